@@ -26,8 +26,8 @@ wit_bindgen::generate!({
 use serde::{Deserialize, Serialize};
 
 use exports::near::agent::channel::{
-    AgentResponse, ChannelConfig, Guest, HttpEndpointConfig, IncomingHttpRequest,
-    OutgoingHttpResponse, StatusUpdate,
+    AgentResponse, ApprovalRequest, ChannelConfig, Guest, HttpEndpointConfig,
+    IncomingHttpRequest, OutgoingHttpResponse, StatusUpdate,
 };
 use near::agent::channel_host::{self, EmittedMessage};
 
@@ -312,6 +312,13 @@ impl Guest for DiscordChannel {
 
     fn on_status(_update: StatusUpdate) {}
 
+    fn on_approval(_request: ApprovalRequest) -> Result<(), String> {
+        // No interactive buttons yet; the host sends a text prompt instead.
+        Err("interactive approvals not supported".to_string())
+    }
+
+    fn on_callback(_payload: String) {}
+
     fn on_shutdown() {
         channel_host::log(
             channel_host::LogLevel::Info,
@@ -9,6 +9,7 @@
 //! - Message event parsing (@mentions, DMs)
 //! - Thread support for conversations
 //! - Response posting via Slack Web API
+//! - Approve/Deny buttons for tool approvals (block actions)
 //!
 //! # Security
 //!
@@ -26,7 +27,7 @@ use serde::{Deserialize, Serialize};
 
 // Re-export generated types
 use exports::near::agent::channel::{
-    AgentResponse, ChannelConfig, Guest, HttpEndpointConfig, IncomingHttpRequest,
+    AgentResponse, ApprovalRequest, ChannelConfig, Guest, HttpEndpointConfig, IncomingHttpRequest,
     OutgoingHttpResponse, StatusUpdate,
 };
 use near::agent::channel_host::{self, EmittedMessage};
@@ -80,6 +81,51 @@ struct SlackEvent {
     subtype: Option<String>,
 }
 
+/// Interactivity payload (button presses), sent form-encoded as `payload=`.
+#[derive(Debug, Deserialize)]
+struct SlackInteraction {
+    /// Interaction type (block_actions, view_submission, etc.)
+    #[serde(rename = "type")]
+    interaction_type: String,
+
+    /// User who pressed the button.
+    user: SlackIdRef,
+
+    /// Channel containing the message.
+    channel: Option<SlackIdRef>,
+
+    /// Team the interaction came from.
+    team: Option<SlackIdRef>,
+
+    /// Message the buttons were attached to.
+    message: Option<SlackInteractionMessage>,
+
+    /// Actions taken (one per button press).
+    #[serde(default)]
+    actions: Vec<SlackAction>,
+}
+
+/// Object reference carrying only an ID.
+#[derive(Debug, Deserialize)]
+struct SlackIdRef {
+    id: String,
+}
+
+/// Message an interaction was attached to.
+#[derive(Debug, Deserialize)]
+struct SlackInteractionMessage {
+    ts: String,
+    thread_ts: Option<String>,
+    text: Option<String>,
+}
+
+/// A single block action.
+#[derive(Debug, Deserialize)]
+struct SlackAction {
+    action_id: String,
+    value: Option<String>,
+}
+
 /// Metadata stored with emitted messages for response routing.
 #[derive(Debug, Serialize, Deserialize)]
 struct SlackMessageMetadata {
@@ -112,6 +158,14 @@ const DM_POLICY_PATH: &str = "state/dm_policy";
 const ALLOW_FROM_PATH: &str = "state/allow_from";
 /// Channel name for pairing store (used by pairing host APIs).
 const CHANNEL_NAME: &str = "slack";
+/// Action IDs of the approval buttons.
+const APPROVE_ACTION: &str = "approval_approve";
+const ALWAYS_ACTION: &str = "approval_always";
+const DENY_ACTION: &str = "approval_deny";
+/// Slack's limit on section block text.
+const SECTION_TEXT_MAX_CHARS: usize = 3000;
+/// Maximum characters shown per tool parameter in an approval prompt.
+const APPROVAL_PARAM_MAX_CHARS: usize = 80;
 
 /// Channel configuration from capabilities file.
 #[derive(Debug, Deserialize)]
@@ -183,6 +237,13 @@ impl Guest for SlackChannel {
             }
         };
 
+        // Interactivity requests (button presses) are form-encoded
+        if !body_str.trim_start().starts_with('{') {
+            if let Some(payload) = form_field(body_str, "payload") {
+                return handle_interaction(&payload);
+            }
+        }
+
         // Parse as Slack event
         let event_wrapper: SlackEventWrapper = match serde_json::from_str(body_str) {
             Ok(e) => e,
@@ -306,6 +367,35 @@ impl Guest for SlackChannel {
 
     fn on_status(_update: StatusUpdate) {}
 
+    fn on_approval(request: ApprovalRequest) -> Result<(), String> {
+        let metadata: SlackMessageMetadata = serde_json::from_str(&request.metadata_json)
+            .map_err(|e| format!("Failed to parse metadata: {}", e))?;
+
+        let text = approval_prompt_text(&request);
+        let mut payload = serde_json::json!({
+            "channel": metadata.channel,
+            "text": text,
+            "blocks": approval_blocks(&text, &request.request_id),
+        });
+        if let Some(thread_ts) = metadata.thread_ts {
+            payload["thread_ts"] = serde_json::Value::String(thread_ts);
+        }
+
+        let ts = slack_api_call("chat.postMessage", &payload)?;
+        channel_host::log(
+            channel_host::LogLevel::Debug,
+            &format!(
+                "Posted approval request {} to Slack channel {}: ts={}",
+                request.request_id,
+                metadata.channel,
+                ts.unwrap_or_default()
+            ),
+        );
+        Ok(())
+    }
+
+    fn on_callback(_payload: String) {}
+
     fn on_shutdown() {
         channel_host::log(channel_host::LogLevel::Info, "Slack channel shutting down");
     }
@@ -444,8 +534,7 @@ fn check_sender_permission(user_id: &str, channel_id: &str, is_dm: bool) -> bool
     }
 
     // 4. Check sender (Slack events only have user ID, not username)
-    let is_allowed =
-        allowed.contains(&"*".to_string()) || allowed.contains(&user_id.to_string());
+    let is_allowed = allowed.contains(&"*".to_string()) || allowed.contains(&user_id.to_string());
 
     if is_allowed {
         return true;
@@ -463,10 +552,7 @@ fn check_sender_permission(user_id: &str, channel_id: &str, is_dm: bool) -> bool
             Ok(result) => {
                 channel_host::log(
                     channel_host::LogLevel::Info,
-                    &format!(
-                        "Pairing request for user {}: code {}",
-                        user_id, result.code
-                    ),
+                    &format!("Pairing request for user {}: code {}", user_id, result.code),
                 );
                 if result.created {
                     let _ = send_pairing_reply(channel_id, &result.code);
@@ -519,6 +605,263 @@ fn send_pairing_reply(channel_id: &str, code: &str) -> Result<(), String> {
     }
 }
 
+// ============================================================================
+// Approvals
+// ============================================================================
+
+/// Render an approval request as plain text.
+fn approval_prompt_text(request: &ApprovalRequest) -> String {
+    let mut text = format!(
+        "Approval needed: {}\n{}",
+        request.tool_name, request.description
+    );
+
+    let params: serde_json::Value =
+        serde_json::from_str(&request.parameters_json).unwrap_or(serde_json::Value::Null);
+    if let Some(obj) = params.as_object().filter(|o| !o.is_empty()) {
+        text.push_str("\n\nParameters:");
+        for (key, value) in obj {
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            text.push_str(&format!(
+                "\n  {}: {}",
+                key,
+                truncate_chars(&value, APPROVAL_PARAM_MAX_CHARS)
+            ));
+        }
+    }
+
+    text
+}
+
+/// Block Kit layout: the prompt followed by Approve / Always / Deny buttons.
+fn approval_blocks(text: &str, request_id: &str) -> serde_json::Value {
+    serde_json::json!([
+        {
+            "type": "section",
+            "text": {
+                "type": "plain_text",
+                "text": truncate_chars(text, SECTION_TEXT_MAX_CHARS - 3),
+            }
+        },
+        {
+            "type": "actions",
+            "block_id": "approval",
+            "elements": [
+                {
+                    "type": "button",
+                    "text": {"type": "plain_text", "text": "Approve"},
+                    "style": "primary",
+                    "action_id": APPROVE_ACTION,
+                    "value": request_id,
+                },
+                {
+                    "type": "button",
+                    "text": {"type": "plain_text", "text": "Always"},
+                    "action_id": ALWAYS_ACTION,
+                    "value": request_id,
+                },
+                {
+                    "type": "button",
+                    "text": {"type": "plain_text", "text": "Deny"},
+                    "style": "danger",
+                    "action_id": DENY_ACTION,
+                    "value": request_id,
+                },
+            ]
+        }
+    ])
+}
+
+/// Map an approval button to (approved, always).
+fn approval_decision(action_id: &str) -> Option<(bool, bool)> {
+    match action_id {
+        APPROVE_ACTION => Some((true, false)),
+        ALWAYS_ACTION => Some((true, true)),
+        DENY_ACTION => Some((false, false)),
+        _ => None,
+    }
+}
+
+/// Message content the agent parses as an approval submission.
+fn approval_submission_json(request_id: &str, approved: bool, always: bool) -> String {
+    serde_json::json!({
+        "ExecApproval": {
+            "request_id": request_id,
+            "approved": approved,
+            "always": always,
+        }
+    })
+    .to_string()
+}
+
+/// Handle an interactivity payload; only approval button presses are used.
+fn handle_interaction(payload: &str) -> OutgoingHttpResponse {
+    let interaction: SlackInteraction = match serde_json::from_str(payload) {
+        Ok(i) => i,
+        Err(e) => {
+            channel_host::log(
+                channel_host::LogLevel::Error,
+                &format!("Failed to parse Slack interaction: {}", e),
+            );
+            return json_response(
+                400,
+                serde_json::json!({"error": "Invalid interaction payload"}),
+            );
+        }
+    };
+
+    if interaction.interaction_type != "block_actions" {
+        channel_host::log(
+            channel_host::LogLevel::Debug,
+            &format!(
+                "Ignoring Slack interaction type: {}",
+                interaction.interaction_type
+            ),
+        );
+        return json_response(200, serde_json::json!({"ok": true}));
+    }
+
+    let (Some(channel), Some(message)) = (interaction.channel, interaction.message) else {
+        return json_response(200, serde_json::json!({"ok": true}));
+    };
+
+    let Some((action, (approved, always))) = interaction
+        .actions
+        .iter()
+        .find_map(|a| approval_decision(&a.action_id).map(|d| (a, d)))
+    else {
+        return json_response(200, serde_json::json!({"ok": true}));
+    };
+    let Some(request_id) = action.value.clone().filter(|v| !v.is_empty()) else {
+        return json_response(200, serde_json::json!({"ok": true}));
+    };
+
+    let user = interaction.user.id;
+    if !check_sender_permission(&user, &channel.id, channel.id.starts_with('D')) {
+        return json_response(200, serde_json::json!({"ok": true}));
+    }
+
+    // Replace the buttons with the outcome so the request can't be pressed twice.
+    let outcome = match (approved, always) {
+        (true, false) => "Approved",
+        (true, true) => "Approved (always)",
+        (false, _) => "Denied",
+    };
+    let update = serde_json::json!({
+        "channel": channel.id,
+        "ts": message.ts,
+        "text": format!(
+            "{}\n\n{} by <@{}>",
+            message.text.clone().unwrap_or_default(),
+            outcome,
+            user
+        ),
+        "blocks": [],
+    });
+    if let Err(e) = slack_api_call("chat.update", &update) {
+        channel_host::log(
+            channel_host::LogLevel::Debug,
+            &format!("Failed to update approval message: {}", e),
+        );
+    }
+
+    // The prompt was posted in the conversation's thread, so its thread_ts
+    // routes the decision back to the session that asked.
+    let thread_ts = message.thread_ts.or(Some(message.ts));
+    emit_message(
+        user,
+        approval_submission_json(&request_id, approved, always),
+        channel.id,
+        thread_ts,
+        interaction.team.map(|t| t.id),
+    );
+
+    json_response(200, serde_json::json!({"ok": true}))
+}
+
+/// POST a JSON payload to a Slack Web API method and return the message ts.
+fn slack_api_call(method: &str, payload: &serde_json::Value) -> Result<Option<String>, String> {
+    let payload_bytes =
+        serde_json::to_vec(payload).map_err(|e| format!("Failed to serialize payload: {}", e))?;
+    let headers = serde_json::json!({"Content-Type": "application/json"});
+
+    let http_response = channel_host::http_request(
+        "POST",
+        &format!("https://slack.com/api/{}", method),
+        &headers.to_string(),
+        Some(&payload_bytes),
+        None,
+    )
+    .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    if http_response.status != 200 {
+        return Err(format!(
+            "Slack API returned status {}",
+            http_response.status
+        ));
+    }
+
+    let slack_response: SlackPostMessageResponse = serde_json::from_slice(&http_response.body)
+        .map_err(|e| format!("Failed to parse Slack response: {}", e))?;
+
+    if !slack_response.ok {
+        return Err(format!(
+            "Slack API error: {}",
+            slack_response
+                .error
+                .unwrap_or_else(|| "unknown".to_string())
+        ));
+    }
+
+    Ok(slack_response.ts)
+}
+
+/// Extract and decode a field from an application/x-www-form-urlencoded body.
+fn form_field(body: &str, name: &str) -> Option<String> {
+    body.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        if key == name {
+            percent_decode(value)
+        } else {
+            None
+        }
+    })
+}
+
+/// Decode a form-encoded value (`+` is a space, `%XX` is a byte).
+fn percent_decode(input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' => {
+                let hex = input.get(i + 1..i + 3)?;
+                out.push(u8::from_str_radix(hex, 16).ok()?);
+                i += 2;
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8(out).ok()
+}
+
+/// Truncate to a character count, appending "..." when cut.
+fn truncate_chars(input: &str, max_chars: usize) -> String {
+    let mut iter = input.chars();
+    let truncated: String = iter.by_ref().take(max_chars).collect();
+    if iter.next().is_some() {
+        format!("{}...", truncated)
+    } else {
+        truncated
+    }
+}
+
 /// Strip leading bot mention from text.
 fn strip_bot_mention(text: &str) -> String {
     // Slack mentions look like <@U12345678>
@@ -551,3 +894,90 @@ fn json_response(status: u16, value: serde_json::Value) -> OutgoingHttpResponse
 
 // Export the component
 export!(SlackChannel);
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_field_decodes_payload() {
+        let body = "token=abc&payload=%7B%22type%22%3A%22block_actions%22%2C%22a%22%3A%22x+y%22%7D";
+        assert_eq!(
+            form_field(body, "payload").as_deref(),
+            Some(r#"{"type":"block_actions","a":"x y"}"#)
+        );
+        assert_eq!(form_field(body, "missing"), None);
+        assert_eq!(form_field(r#"{"type":"event_callback"}"#, "payload"), None);
+    }
+
+    #[test]
+    fn test_percent_decode_rejects_truncated_escape() {
+        assert_eq!(percent_decode("abc%2"), None);
+        assert_eq!(percent_decode("%zz"), None);
+    }
+
+    #[test]
+    fn test_parse_block_actions_interaction() {
+        let json = r#"{
+            "type": "block_actions",
+            "user": {"id": "U123", "username": "jane"},
+            "channel": {"id": "D456", "name": "directmessage"},
+            "team": {"id": "T789"},
+            "message": {"ts": "1700000000.000200", "thread_ts": "1700000000.000100", "text": "Approval needed"},
+            "actions": [{"action_id": "approval_deny", "block_id": "approval", "value": "req-1"}]
+        }"#;
+
+        let interaction: SlackInteraction = serde_json::from_str(json).unwrap();
+        assert_eq!(interaction.interaction_type, "block_actions");
+        assert_eq!(interaction.user.id, "U123");
+        let message = interaction.message.unwrap();
+        assert_eq!(message.thread_ts.as_deref(), Some("1700000000.000100"));
+        let action = &interaction.actions[0];
+        assert_eq!(approval_decision(&action.action_id), Some((false, false)));
+        assert_eq!(action.value.as_deref(), Some("req-1"));
+    }
+
+    #[test]
+    fn test_approval_blocks_carry_request_id() {
+        let blocks = approval_blocks("Approval needed: send_email", "req-1");
+        let buttons = blocks[1]["elements"].as_array().unwrap();
+        let decisions: Vec<_> = buttons
+            .iter()
+            .map(|b| {
+                assert_eq!(b["value"], "req-1");
+                approval_decision(b["action_id"].as_str().unwrap()).unwrap()
+            })
+            .collect();
+        assert_eq!(decisions, vec![(true, false), (true, true), (false, false)]);
+    }
+
+    #[test]
+    fn test_approval_submission_json_matches_agent_format() {
+        let value: serde_json::Value =
+            serde_json::from_str(&approval_submission_json("req-1", true, true)).unwrap();
+        assert_eq!(value["ExecApproval"]["request_id"], "req-1");
+        assert_eq!(value["ExecApproval"]["approved"], true);
+        assert_eq!(value["ExecApproval"]["always"], true);
+    }
+
+    #[test]
+    fn test_approval_prompt_text_lists_truncated_parameters() {
+        let request = ApprovalRequest {
+            request_id: "req-1".to_string(),
+            tool_name: "send_email".to_string(),
+            description: "Send engagement letter".to_string(),
+            parameters_json:
+                serde_json::json!({"to": "client@example.com", "body": "x".repeat(200)}).to_string(),
+            metadata_json: "{}".to_string(),
+        };
+
+        let text = approval_prompt_text(&request);
+        assert!(text.starts_with("Approval needed: send_email\nSend engagement letter"));
+        assert!(text.contains("  to: client@example.com"));
+        assert!(text.contains(&format!("  body: {}...", "x".repeat(80))));
+    }
+}
@@ -13,6 +13,7 @@
 //! - Group chat support with @mention triggering
 //! - Reply threading support
 //! - User name extraction
+//! - Approve/Deny inline keyboards for tool approvals
 //!
 //! # Security
 //!
@@ -30,7 +31,7 @@ use serde::{Deserialize, Serialize};
 
 // Re-export generated types
 use exports::near::agent::channel::{
-    AgentResponse, ChannelConfig, Guest, HttpEndpointConfig, IncomingHttpRequest,
+    AgentResponse, ApprovalRequest, ChannelConfig, Guest, HttpEndpointConfig, IncomingHttpRequest,
     OutgoingHttpResponse, PollConfig, StatusType, StatusUpdate,
 };
 use near::agent::channel_host::{self, EmittedMessage};
@@ -54,6 +55,26 @@ struct TelegramUpdate {
 
     /// Channel post (we ignore these for now).
     channel_post: Option<TelegramMessage>,
+
+    /// Inline keyboard button press.
+    callback_query: Option<TelegramCallbackQuery>,
+}
+
+/// Telegram CallbackQuery object (inline keyboard button press).
+/// https://core.telegram.org/bots/api#callbackquery
+#[derive(Debug, Deserialize)]
+struct TelegramCallbackQuery {
+    /// Unique query identifier, passed to answerCallbackQuery.
+    id: String,
+
+    /// User who pressed the button.
+    from: TelegramUser,
+
+    /// Message the keyboard was attached to.
+    message: Option<TelegramMessage>,
+
+    /// Data attached to the pressed button.
+    data: Option<String>,
 }
 
 /// Telegram Message object.
@@ -81,6 +102,28 @@ struct TelegramMessage {
 
     /// Bot command entities (for /commands).
     entities: Option<Vec<MessageEntity>>,
+
+    /// Voice note recorded in the Telegram client.
+    #[serde(default)]
+    voice: Option<TelegramVoice>,
+}
+
+/// Telegram Voice object.
+/// https://core.telegram.org/bots/api#voice
+#[derive(Debug, Deserialize)]
+struct TelegramVoice {
+    /// Identifier used to download the file.
+    file_id: String,
+
+    /// Duration in seconds.
+    #[serde(default)]
+    duration: u32,
+
+    /// MIME type as defined by the sender (usually audio/ogg).
+    mime_type: Option<String>,
+
+    /// File size in bytes.
+    file_size: Option<u64>,
 }
 
 /// Telegram User object.
@@ -158,8 +201,9 @@ struct SentMessage {
     message_id: i64,
 }
 
-/// Workspace path for storing polling state.
-const POLLING_STATE_PATH: &str = "state/last_update_id";
+/// State key for the last processed update offset (persisted by the host
+/// so polling resumes where it left off after a restart).
+const POLLING_OFFSET_KEY: &str = "last_update_id";
 
 /// Workspace path for persisting owner_id across WASM callbacks.
 const OWNER_ID_PATH: &str = "state/owner_id";
@@ -179,6 +223,9 @@ const BOT_USERNAME_PATH: &str = "state/bot_username";
 /// Workspace path for persisting respond_to_all_group_messages flag.
 const RESPOND_TO_ALL_GROUP_PATH: &str = "state/respond_to_all_group_messages";
 
+/// Update types requested from Telegram (polling and webhook).
+const ALLOWED_UPDATES: [&str; 3] = ["message", "edited_message", "callback_query"];
+
 // ============================================================================
 // Channel Metadata
 // ============================================================================
@@ -273,8 +320,10 @@ fn status_message_for_user(update: &StatusUpdate) -> Option<String> {
 
 fn get_updates_url(offset: i64, timeout_secs: u32) -> String {
     format!(
-        "https://api.telegram.org/bot{{TELEGRAM_BOT_TOKEN}}/getUpdates?offset={}&timeout={}&allowed_updates=[\"message\",\"edited_message\"]",
-        offset, timeout_secs
+        "https://api.telegram.org/bot{{TELEGRAM_BOT_TOKEN}}/getUpdates?offset={}&timeout={}&allowed_updates={}",
+        offset,
+        timeout_secs,
+        serde_json::json!(ALLOWED_UPDATES)
     )
 }
 
@@ -472,8 +521,8 @@ impl Guest for TelegramChannel {
     }
 
     fn on_poll() {
-        // Read last offset from workspace storage
-        let offset = match channel_host::workspace_read(POLLING_STATE_PATH) {
+        // Read last offset from persistent channel state
+        let offset = match channel_host::state_get(POLLING_OFFSET_KEY) {
             Some(s) => s.parse::<i64>().unwrap_or(0),
             None => 0,
         };
@@ -546,8 +595,8 @@ impl Guest for TelegramChannel {
 
                             // Save new offset if it changed
                             if new_offset != offset {
-                                if let Err(e) = channel_host::workspace_write(
-                                    POLLING_STATE_PATH,
+                                if let Err(e) = channel_host::state_set(
+                                    POLLING_OFFSET_KEY,
                                     &new_offset.to_string(),
                                 ) {
                                     channel_host::log(
@@ -711,6 +760,47 @@ impl Guest for TelegramChannel {
         }
     }
 
+    fn on_approval(request: ApprovalRequest) -> Result<(), String> {
+        let metadata: TelegramMessageMetadata = serde_json::from_str(&request.metadata_json)
+            .map_err(|e| format!("Failed to parse metadata: {}", e))?;
+
+        let text = approval_prompt_text(&request);
+        let keyboard = approval_keyboard(&request.request_id);
+
+        // Plain text: tool parameters routinely contain Markdown metacharacters.
+        let msg_id = match send_message_with_markup(
+            metadata.chat_id,
+            &text,
+            Some(metadata.message_id),
+            None,
+            Some(&keyboard),
+        ) {
+            Ok(id) => id,
+            Err(first_err) => {
+                channel_host::log(
+                    channel_host::LogLevel::Warn,
+                    &format!(
+                        "Failed to send approval reply ({}), retrying without reply context",
+                        first_err
+                    ),
+                );
+                send_message_with_markup(metadata.chat_id, &text, None, None, Some(&keyboard))
+                    .map_err(|e| e.to_string())?
+            }
+        };
+
+        channel_host::log(
+            channel_host::LogLevel::Debug,
+            &format!(
+                "Sent approval request {} to chat {}: message_id={}",
+                request.request_id, metadata.chat_id, msg_id
+            ),
+        );
+        Ok(())
+    }
+
+    fn on_callback(_payload: String) {}
+
     fn on_shutdown() {
         channel_host::log(
             channel_host::LogLevel::Info,
@@ -750,12 +840,27 @@ fn send_message(
     text: &str,
     reply_to_message_id: Option<i64>,
     parse_mode: Option<&str>,
+) -> Result<i64, SendError> {
+    send_message_with_markup(chat_id, text, reply_to_message_id, parse_mode, None)
+}
+
+/// Send a message with an optional reply_markup (e.g. an inline keyboard).
+fn send_message_with_markup(
+    chat_id: i64,
+    text: &str,
+    reply_to_message_id: Option<i64>,
+    parse_mode: Option<&str>,
+    reply_markup: Option<&serde_json::Value>,
 ) -> Result<i64, SendError> {
     let mut payload = serde_json::json!({
         "chat_id": chat_id,
         "text": text,
     });
 
+    if let Some(markup) = reply_markup {
+        payload["reply_markup"] = markup.clone();
+    }
+
     if let Some(message_id) = reply_to_message_id {
         payload["reply_to_message_id"] = serde_json::Value::Number(message_id.into());
     }
@@ -877,7 +982,7 @@ fn register_webhook(tunnel_url: &str, webhook_secret: Option<&str>) -> Result<()
     // Build setWebhook request body
     let mut body = serde_json::json!({
         "url": webhook_url,
-        "allowed_updates": ["message", "edited_message"]
+        "allowed_updates": ALLOWED_UPDATES
     });
 
     if let Some(secret) = webhook_secret {
@@ -967,6 +1072,10 @@ fn handle_update(update: TelegramUpdate) {
     if let Some(message) = update.edited_message {
         handle_message(message);
     }
+
+    if let Some(query) = update.callback_query {
+        handle_callback_query(query);
+    }
 }
 
 /// Process a single message.
@@ -978,7 +1087,7 @@ fn handle_message(message: TelegramMessage) {
         .or_else(|| message.caption.filter(|c| !c.is_empty()))
         .unwrap_or_default();
 
-    if content.is_empty() {
+    if content.is_empty() && message.voice.is_none() {
         return;
     }
 
@@ -1113,16 +1222,22 @@ fn handle_message(message: TelegramMessage) {
     let metadata_json = serde_json::to_string(&metadata).unwrap_or_else(|_| "{}".to_string());
 
     let bot_username = channel_host::workspace_read(BOT_USERNAME_PATH).unwrap_or_default();
-    let content_to_emit = match content_to_emit_for_agent(
+    let text_to_emit = content_to_emit_for_agent(
         &content,
         if bot_username.is_empty() {
             None
         } else {
             Some(bot_username.as_str())
         },
-    ) {
-        Some(value) => value,
-        None => return,
+    );
+    // Voice notes are transcribed only after the sender passed the checks above.
+    let content_to_emit = match (message.voice, text_to_emit) {
+        (Some(voice), caption) => {
+            let transcript = transcribe_voice(&voice, &user_name);
+            voice_note_content(caption.as_deref(), transcript)
+        }
+        (None, Some(value)) => value,
+        (None, None) => return,
     };
 
     // Emit the message to the agent
@@ -1143,6 +1258,316 @@ fn handle_message(message: TelegramMessage) {
     );
 }
 
+// ============================================================================
+// Voice Notes
+// ============================================================================
+
+/// Largest file the Bot API lets bots download.
+const MAX_VOICE_BYTES: u64 = 20 * 1024 * 1024;
+
+/// Download a voice note and transcribe it through the host.
+fn transcribe_voice(voice: &TelegramVoice, sender: &str) -> Result<String, String> {
+    if voice.file_size.is_some_and(|size| size > MAX_VOICE_BYTES) {
+        return Err("voice note is too large to download".to_string());
+    }
+    let audio = download_file(&voice.file_id)?;
+    let mime_type = voice.mime_type.as_deref().unwrap_or("audio/ogg");
+    channel_host::log(
+        channel_host::LogLevel::Debug,
+        &format!(
+            "Transcribing {}s voice note ({} bytes)",
+            voice.duration,
+            audio.len()
+        ),
+    );
+    channel_host::transcribe_audio(&audio, mime_type, sender, &voice_audio_ref(&voice.file_id))
+}
+
+/// Where the original audio can be fetched again (file IDs stay valid for the bot).
+fn voice_audio_ref(file_id: &str) -> String {
+    format!("telegram:file/{}", file_id)
+}
+
+/// Text emitted to the agent for a voice note, with any caption first.
+fn voice_note_content(caption: Option<&str>, transcript: Result<String, String>) -> String {
+    let body = match transcript {
+        Ok(text) if !text.trim().is_empty() => format!("[Voice note] {}", text.trim()),
+        Ok(_) => "[Voice note with no speech detected]".to_string(),
+        Err(e) => format!("[Voice note could not be transcribed: {}]", e),
+    };
+    match caption.filter(|c| !c.trim().is_empty()) {
+        Some(caption) => format!("{}\n\n{}", caption, body),
+        None => body,
+    }
+}
+
+/// Fetch a file's bytes via getFile and the file download endpoint.
+fn download_file(file_id: &str) -> Result<Vec<u8>, String> {
+    // File IDs are URL-safe base64; refuse anything that would need escaping.
+    if file_id.is_empty()
+        || !file_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err("invalid file_id".to_string());
+    }
+    let headers = serde_json::json!({});
+    let response = channel_host::http_request(
+        "GET",
+        &format!(
+            "https://api.telegram.org/bot{{TELEGRAM_BOT_TOKEN}}/getFile?file_id={}",
+            file_id
+        ),
+        &headers.to_string(),
+        None,
+        None,
+    )
+    .map_err(|e| format!("getFile failed: {}", e))?;
+    if response.status != 200 {
+        return Err(format!("getFile returned {}", response.status));
+    }
+    let file_path = parse_file_path(&response.body)?;
+
+    let response = channel_host::http_request(
+        "GET",
+        &format!(
+            "https://api.telegram.org/file/bot{{TELEGRAM_BOT_TOKEN}}/{}",
+            file_path
+        ),
+        &headers.to_string(),
+        None,
+        None,
+    )
+    .map_err(|e| format!("file download failed: {}", e))?;
+    if response.status != 200 {
+        return Err(format!("file download returned {}", response.status));
+    }
+    Ok(response.body)
+}
+
+/// Extract `result.file_path` from a getFile response.
+fn parse_file_path(body: &[u8]) -> Result<String, String> {
+    let value: serde_json::Value =
+        serde_json::from_slice(body).map_err(|e| format!("invalid getFile response: {}", e))?;
+    value
+        .get("result")
+        .and_then(|r| r.get("file_path"))
+        .and_then(|p| p.as_str())
+        .filter(|p| !p.is_empty() && !p.contains(".."))
+        .map(str::to_string)
+        .ok_or_else(|| "getFile response has no file_path".to_string())
+}
+
+// ============================================================================
+// Approvals
+// ============================================================================
+
+/// Maximum characters shown per tool parameter in an approval prompt.
+const APPROVAL_PARAM_MAX_CHARS: usize = 80;
+
+/// A decision carried in an approval button's callback_data.
+#[derive(Debug, PartialEq, Eq)]
+struct ApprovalDecision {
+    request_id: String,
+    approved: bool,
+    always: bool,
+}
+
+/// Render an approval request as plain text.
+fn approval_prompt_text(request: &ApprovalRequest) -> String {
+    let mut text = format!(
+        "Approval needed: {}\n{}",
+        request.tool_name, request.description
+    );
+
+    let params: serde_json::Value =
+        serde_json::from_str(&request.parameters_json).unwrap_or(serde_json::Value::Null);
+    if let Some(obj) = params.as_object().filter(|o| !o.is_empty()) {
+        text.push_str("\n\nParameters:");
+        for (key, value) in obj {
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            text.push_str(&format!(
+                "\n  {}: {}",
+                key,
+                truncate_status_message(&value, APPROVAL_PARAM_MAX_CHARS)
+            ));
+        }
+    }
+
+    text
+}
+
+/// Inline keyboard with Approve / Always / Deny buttons for a request.
+///
+/// callback_data is limited to 64 bytes; a UUID plus prefix fits.
+fn approval_keyboard(request_id: &str) -> serde_json::Value {
+    serde_json::json!({
+        "inline_keyboard": [[
+            {"text": "Approve", "callback_data": format!("approve:{}", request_id)},
+            {"text": "Always", "callback_data": format!("always:{}", request_id)},
+            {"text": "Deny", "callback_data": format!("deny:{}", request_id)},
+        ]]
+    })
+}
+
+/// Parse callback_data produced by `approval_keyboard`.
+fn parse_approval_callback(data: &str) -> Option<ApprovalDecision> {
+    let (action, request_id) = data.split_once(':')?;
+    let (approved, always) = match action {
+        "approve" => (true, false),
+        "always" => (true, true),
+        "deny" => (false, false),
+        _ => return None,
+    };
+    if request_id.is_empty() {
+        return None;
+    }
+    Some(ApprovalDecision {
+        request_id: request_id.to_string(),
+        approved,
+        always,
+    })
+}
+
+/// Message content the agent parses as an approval submission.
+fn approval_submission_json(decision: &ApprovalDecision) -> String {
+    serde_json::json!({
+        "ExecApproval": {
+            "request_id": decision.request_id,
+            "approved": decision.approved,
+            "always": decision.always,
+        }
+    })
+    .to_string()
+}
+
+/// Handle an inline keyboard press on an approval prompt.
+fn handle_callback_query(query: TelegramCallbackQuery) {
+    let Some(decision) = query.data.as_deref().and_then(parse_approval_callback) else {
+        let _ = answer_callback_query(&query.id, None);
+        return;
+    };
+
+    let Some(message) = query.message else {
+        let _ = answer_callback_query(&query.id, Some("This request has expired."));
+        return;
+    };
+
+    // Only the owner may decide when owner_id is set. Otherwise the decision
+    // is emitted as the presser, and only resolves if that user's session is
+    // the one waiting on this request.
+    let owner_id = channel_host::workspace_read(OWNER_ID_PATH)
+        .filter(|s| !s.is_empty())
+        .and_then(|s| s.parse::<i64>().ok());
+    if let Some(owner_id) = owner_id {
+        if query.from.id != owner_id {
+            channel_host::log(
+                channel_host::LogLevel::Debug,
+                &format!(
+                    "Ignoring approval press from non-owner user {} (owner: {})",
+                    query.from.id, owner_id
+                ),
+            );
+            let _ = answer_callback_query(&query.id, Some("Only the owner can approve."));
+            return;
+        }
+    }
+
+    let outcome = match (decision.approved, decision.always) {
+        (true, false) => "Approved",
+        (true, true) => "Approved (always)",
+        (false, _) => "Denied",
+    };
+    let _ = answer_callback_query(&query.id, Some(outcome));
+
+    // Replace the buttons with the outcome so the request can't be pressed twice.
+    let original = message.text.clone().unwrap_or_default();
+    if let Err(e) = telegram_post(
+        "editMessageText",
+        &serde_json::json!({
+            "chat_id": message.chat.id,
+            "message_id": message.message_id,
+            "text": format!("{}\n\n{}", original, outcome),
+        }),
+    ) {
+        channel_host::log(
+            channel_host::LogLevel::Debug,
+            &format!("Failed to update approval message: {}", e),
+        );
+    }
+
+    let user_name = if let Some(ref last) = query.from.last_name {
+        format!("{} {}", query.from.first_name, last)
+    } else {
+        query.from.first_name.clone()
+    };
+
+    let metadata = TelegramMessageMetadata {
+        chat_id: message.chat.id,
+        message_id: message.message_id,
+        user_id: query.from.id,
+        is_private: message.chat.chat_type == "private",
+    };
+    let metadata_json = serde_json::to_string(&metadata).unwrap_or_else(|_| "{}".to_string());
+
+    channel_host::emit_message(&EmittedMessage {
+        user_id: query.from.id.to_string(),
+        user_name: Some(user_name),
+        content: approval_submission_json(&decision),
+        thread_id: None,
+        metadata_json,
+    });
+
+    channel_host::log(
+        channel_host::LogLevel::Debug,
+        &format!(
+            "Emitted approval decision for request {} from user {}",
+            decision.request_id, query.from.id
+        ),
+    );
+}
+
+/// Acknowledge a callback query so the client stops its loading spinner.
+fn answer_callback_query(query_id: &str, text: Option<&str>) -> Result<(), String> {
+    let mut payload = serde_json::json!({ "callback_query_id": query_id });
+    if let Some(text) = text {
+        payload["text"] = serde_json::Value::String(text.to_string());
+    }
+    telegram_post("answerCallbackQuery", &payload)
+}
+
+/// POST a JSON payload to a Bot API method, checking only the HTTP status.
+fn telegram_post(method: &str, payload: &serde_json::Value) -> Result<(), String> {
+    let payload_bytes =
+        serde_json::to_vec(payload).map_err(|e| format!("Failed to serialize payload: {}", e))?;
+    let headers = serde_json::json!({ "Content-Type": "application/json" });
+
+    let result = channel_host::http_request(
+        "POST",
+        &format!(
+            "https://api.telegram.org/bot{{TELEGRAM_BOT_TOKEN}}/{}",
+            method
+        ),
+        &headers.to_string(),
+        Some(&payload_bytes),
+        None,
+    );
+
+    match result {
+        Ok(response) if response.status == 200 => Ok(()),
+        Ok(response) => Err(format!(
+            "{} returned {}: {}",
+            method,
+            response.status,
+            String::from_utf8_lossy(&response.body)
+        )),
+        Err(e) => Err(format!("HTTP request failed: {}", e)),
+    }
+}
+
 /// Clean message text by removing bot commands and @mentions at the start.
 /// When bot_username is set, only strips that specific mention; otherwise strips any leading @mention.
 fn clean_message_text(text: &str, bot_username: Option<&str>) -> String {
@@ -1476,12 +1901,153 @@ mod tests {
         assert_eq!(msg.caption.as_deref(), Some("What's in this image?"));
     }
 
+    #[test]
+    fn test_parse_voice_message() {
+        let json = r#"{
+            "message_id": 2,
+            "from": {"id": 1, "is_bot": false, "first_name": "A"},
+            "chat": {"id": 1, "type": "private"},
+            "voice": {
+                "file_id": "AwACAgQAAxkBAAIB",
+                "file_unique_id": "AgADxw",
+                "duration": 7,
+                "mime_type": "audio/ogg",
+                "file_size": 23040
+            }
+        }"#;
+        let msg: TelegramMessage = serde_json::from_str(json).unwrap();
+        assert_eq!(msg.text, None);
+        let voice = msg.voice.unwrap();
+        assert_eq!(voice.file_id, "AwACAgQAAxkBAAIB");
+        assert_eq!(voice.duration, 7);
+        assert_eq!(voice.mime_type.as_deref(), Some("audio/ogg"));
+        assert_eq!(voice.file_size, Some(23040));
+        assert_eq!(
+            voice_audio_ref(&voice.file_id),
+            "telegram:file/AwACAgQAAxkBAAIB"
+        );
+    }
+
+    #[test]
+    fn test_voice_note_content() {
+        assert_eq!(
+            voice_note_content(None, Ok("  Call me back.  ".to_string())),
+            "[Voice note] Call me back."
+        );
+        assert_eq!(
+            voice_note_content(Some("re: lease"), Ok("Call me back.".to_string())),
+            "re: lease\n\n[Voice note] Call me back."
+        );
+        assert_eq!(
+            voice_note_content(None, Ok(String::new())),
+            "[Voice note with no speech detected]"
+        );
+        assert_eq!(
+            voice_note_content(None, Err("transcription is not configured".to_string())),
+            "[Voice note could not be transcribed: transcription is not configured]"
+        );
+    }
+
+    #[test]
+    fn test_parse_file_path() {
+        let body = br#"{"ok":true,"result":{"file_id":"x","file_path":"voice/file_12.oga"}}"#;
+        assert_eq!(parse_file_path(body).unwrap(), "voice/file_12.oga");
+        assert!(parse_file_path(br#"{"ok":false}"#).is_err());
+        assert!(parse_file_path(br#"{"ok":true,"result":{"file_path":"../x"}}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_callback_query_update() {
+        let json = r#"{
+            "update_id": 124,
+            "callback_query": {
+                "id": "cbq-1",
+                "from": {"id": 789, "is_bot": false, "first_name": "John"},
+                "message": {
+                    "message_id": 457,
+                    "chat": {"id": 789, "type": "private"},
+                    "text": "Approval needed: send_email"
+                },
+                "data": "approve:2f1c9a52-6f0e-4c55-9d43-5d6c2a1b0e11"
+            }
+        }"#;
+
+        let update: TelegramUpdate = serde_json::from_str(json).unwrap();
+        let query = update.callback_query.unwrap();
+        assert_eq!(query.id, "cbq-1");
+        assert_eq!(query.from.id, 789);
+        assert_eq!(query.message.unwrap().message_id, 457);
+        assert_eq!(
+            query.data.as_deref(),
+            Some("approve:2f1c9a52-6f0e-4c55-9d43-5d6c2a1b0e11")
+        );
+    }
+
+    #[test]
+    fn test_approval_keyboard_round_trips_decisions() {
+        let request_id = "2f1c9a52-6f0e-4c55-9d43-5d6c2a1b0e11";
+        let keyboard = approval_keyboard(request_id);
+        let buttons = keyboard["inline_keyboard"][0].as_array().unwrap();
+        assert_eq!(buttons.len(), 3);
+
+        let decisions: Vec<ApprovalDecision> = buttons
+            .iter()
+            .map(|b| {
+                let data = b["callback_data"].as_str().unwrap();
+                assert!(data.len() <= 64, "callback_data over Telegram's limit");
+                parse_approval_callback(data).unwrap()
+            })
+            .collect();
+
+        assert_eq!((decisions[0].approved, decisions[0].always), (true, false));
+        assert_eq!((decisions[1].approved, decisions[1].always), (true, true));
+        assert_eq!((decisions[2].approved, decisions[2].always), (false, false));
+        assert!(decisions.iter().all(|d| d.request_id == request_id));
+    }
+
+    #[test]
+    fn test_parse_approval_callback_rejects_unknown() {
+        assert_eq!(parse_approval_callback("maybe:abc"), None);
+        assert_eq!(parse_approval_callback("approve:"), None);
+        assert_eq!(parse_approval_callback("approve"), None);
+    }
+
+    #[test]
+    fn test_approval_submission_json_matches_agent_format() {
+        let json = approval_submission_json(&ApprovalDecision {
+            request_id: "abc".to_string(),
+            approved: false,
+            always: false,
+        });
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["ExecApproval"]["request_id"], "abc");
+        assert_eq!(value["ExecApproval"]["approved"], false);
+        assert_eq!(value["ExecApproval"]["always"], false);
+    }
+
+    #[test]
+    fn test_approval_prompt_text_lists_truncated_parameters() {
+        let request = ApprovalRequest {
+            request_id: "abc".to_string(),
+            tool_name: "send_email".to_string(),
+            description: "Send engagement letter".to_string(),
+            parameters_json:
+                serde_json::json!({"to": "client@example.com", "body": "x".repeat(200)}).to_string(),
+            metadata_json: "{}".to_string(),
+        };
+
+        let text = approval_prompt_text(&request);
+        assert!(text.starts_with("Approval needed: send_email\nSend engagement letter"));
+        assert!(text.contains("  to: client@example.com"));
+        assert!(text.contains(&format!("  body: {}...", "x".repeat(80))));
+    }
+
     #[test]
     fn test_get_updates_url_includes_offset_and_timeout() {
         let url = get_updates_url(444_809_884, 30);
         assert!(url.contains("offset=444809884"));
         assert!(url.contains("timeout=30"));
-        assert!(url.contains("allowed_updates=[\"message\",\"edited_message\"]"));
+        assert!(url.contains("allowed_updates=[\"message\",\"edited_message\",\"callback_query\"]"));
     }
 
     #[test]
@@ -10,6 +10,9 @@
 //!
 //! - Webhook-based message receiving (WhatsApp is webhook-only, no polling)
 //! - Text message support
+//! - Media ingestion (image, audio, video, document, sticker) into the workspace
+//! - Delivery status events (sent, delivered, read, failed)
+//! - Template-message replies outside the 24-hour customer service window
 //! - Business account support
 //! - User name extraction from contacts
 //!
@@ -17,7 +20,7 @@
 //!
 //! - Access token is injected by host during HTTP requests via {WHATSAPP_ACCESS_TOKEN} placeholder
 //! - WASM never sees raw credentials
-//! - Webhook verify token validation by host
+//! - Webhook verify token (GET) and X-Hub-Signature-256 (POST) validation by host
 
 // Generate bindings from the WIT file
 wit_bindgen::generate!({
@@ -29,7 +32,7 @@ use serde::{Deserialize, Serialize};
 
 // Re-export generated types
 use exports::near::agent::channel::{
-    AgentResponse, ChannelConfig, Guest, HttpEndpointConfig, IncomingHttpRequest,
+    AgentResponse, ApprovalRequest, ChannelConfig, Guest, HttpEndpointConfig, IncomingHttpRequest,
     OutgoingHttpResponse, StatusUpdate,
 };
 use near::agent::channel_host::{self, EmittedMessage};
@@ -137,10 +140,61 @@ struct WhatsAppMessage {
     /// Text content (if type is "text")
     text: Option<TextContent>,
 
+    /// Media content, present for the matching message type.
+    image: Option<MediaContent>,
+    audio: Option<MediaContent>,
+    video: Option<MediaContent>,
+    document: Option<MediaContent>,
+    sticker: Option<MediaContent>,
+
     /// Context for replies
     context: Option<MessageContext>,
 }
 
+impl WhatsAppMessage {
+    /// Media payload for media message types.
+    fn media(&self) -> Option<&MediaContent> {
+        match self.message_type.as_str() {
+            "image" => self.image.as_ref(),
+            "audio" => self.audio.as_ref(),
+            "video" => self.video.as_ref(),
+            "document" => self.document.as_ref(),
+            "sticker" => self.sticker.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Media attached to an incoming message.
+#[derive(Debug, Deserialize)]
+struct MediaContent {
+    /// Media ID (resolved to a download URL via the Graph API)
+    id: String,
+
+    /// MIME type reported by WhatsApp
+    mime_type: Option<String>,
+
+    /// SHA-256 of the media file
+    sha256: Option<String>,
+
+    /// Caption (image, video, document)
+    caption: Option<String>,
+
+    /// Original file name (document)
+    filename: Option<String>,
+}
+
+/// Response from the media URL lookup (GET /{media-id}).
+#[derive(Debug, Deserialize)]
+struct MediaUrlResponse {
+    /// Short-lived download URL (requires the access token)
+    url: String,
+
+    mime_type: Option<String>,
+
+    file_size: Option<u64>,
+}
+
 /// Text message content.
 #[derive(Debug, Deserialize)]
 struct TextContent {
@@ -172,6 +226,18 @@ struct MessageStatus {
 
     /// Recipient ID
     recipient_id: String,
+
+    /// Failure details (status "failed")
+    #[serde(default)]
+    errors: Vec<StatusError>,
+}
+
+/// Error attached to a failed message status.
+#[derive(Debug, Deserialize)]
+struct StatusError {
+    code: i64,
+
+    title: Option<String>,
 }
 
 /// WhatsApp API response wrapper.
@@ -235,6 +301,31 @@ const ALLOW_FROM_PATH: &str = "state/allow_from";
 /// Channel name for pairing store (used by pairing host APIs).
 const CHANNEL_NAME: &str = "whatsapp";
 
+/// State key for the approved template used outside the service window.
+const TEMPLATE_NAME_KEY: &str = "config:template_name";
+/// State key for the template language code.
+const TEMPLATE_LANGUAGE_KEY: &str = "config:template_language";
+/// State key prefix for the last inbound message time per sender (unix secs).
+const LAST_INBOUND_PREFIX: &str = "last_inbound:";
+
+/// WhatsApp only allows free-form replies within 24h of the user's last message.
+const SERVICE_WINDOW_SECS: u64 = 24 * 60 * 60;
+/// Margin so a reply sent right at the edge of the window is not rejected.
+const SERVICE_WINDOW_MARGIN_SECS: u64 = 60;
+/// WhatsApp caps template body parameters at 1024 characters.
+const MAX_TEMPLATE_PARAM_CHARS: usize = 1024;
+
+/// Workspace directory (under channels/whatsapp/) for ingested media records.
+/// The host mirrors this directory into the agent workspace.
+const MEDIA_DIR: &str = "media";
+/// Largest media file downloaded for ingestion.
+const MAX_MEDIA_BYTES: u64 = 5 * 1024 * 1024;
+/// Largest text document stored inline in a media record.
+const MAX_INLINE_TEXT_BYTES: usize = 256 * 1024;
+
+/// Error code WhatsApp returns when the customer service window has closed.
+const ERROR_REENGAGEMENT: i64 = 131047;
+
 /// Channel configuration from capabilities file.
 #[derive(Debug, Deserialize)]
 struct WhatsAppConfig {
@@ -254,6 +345,15 @@ struct WhatsAppConfig {
 
     #[serde(default)]
     allow_from: Option<Vec<String>>,
+
+    /// Approved message template sent when replying outside the 24h window.
+    /// The reply text is passed as the template's first body parameter.
+    #[serde(default)]
+    template_name: Option<String>,
+
+    /// Template language code (default: en_US).
+    #[serde(default)]
+    template_language: Option<String>,
 }
 
 fn default_api_version() -> String {
@@ -285,6 +385,8 @@ impl Guest for WhatsAppChannel {
                     owner_id: None,
                     dm_policy: None,
                     allow_from: None,
+                    template_name: None,
+                    template_language: None,
                 }
             }
         };
@@ -318,6 +420,19 @@ impl Guest for WhatsAppChannel {
             .unwrap_or_else(|_| "[]".to_string());
         let _ = channel_host::workspace_write(ALLOW_FROM_PATH, &allow_from_json);
 
+        // Persist template config for out-of-window replies in on_respond()
+        match config.template_name.as_deref().filter(|t| !t.is_empty()) {
+            Some(template) => {
+                let _ = channel_host::state_set(TEMPLATE_NAME_KEY, template);
+                let language = config.template_language.as_deref().unwrap_or("en_US");
+                let _ = channel_host::state_set(TEMPLATE_LANGUAGE_KEY, language);
+            }
+            None => {
+                let _ = channel_host::state_delete(TEMPLATE_NAME_KEY);
+                let _ = channel_host::state_delete(TEMPLATE_LANGUAGE_KEY);
+            }
+        }
+
         // WhatsApp Cloud API is webhook-only, no polling available
         Ok(ChannelConfig {
             display_name: "WhatsApp".to_string(),
@@ -390,17 +505,36 @@ impl Guest for WhatsAppChannel {
             api_version, metadata.phone_number_id
         );
 
-        // Build sendMessage payload
-        let payload = serde_json::json!({
-            "messaging_product": "whatsapp",
-            "recipient_type": "individual",
-            "to": metadata.sender_phone,  // Original sender becomes recipient
-            "type": "text",
-            "text": {
-                "preview_url": false,
-                "body": response.content
-            }
-        });
+        // Free-form text is only allowed within 24h of the user's last
+        // message; after that WhatsApp requires an approved template.
+        let last_inbound = last_inbound_secs(&metadata.sender_phone, &metadata.timestamp);
+        let now_secs = channel_host::now_millis() / 1000;
+        let payload = if within_service_window(last_inbound, now_secs) {
+            text_payload(&metadata.sender_phone, &response.content)
+        } else {
+            let template = channel_host::state_get(TEMPLATE_NAME_KEY).ok_or_else(|| {
+                format!(
+                    "Cannot reply to {}: more than 24h since their last message and no \
+                     template_name is configured",
+                    metadata.sender_phone
+                )
+            })?;
+            let language = channel_host::state_get(TEMPLATE_LANGUAGE_KEY)
+                .unwrap_or_else(|| "en_US".to_string());
+            channel_host::log(
+                channel_host::LogLevel::Info,
+                &format!(
+                    "Service window closed for {}, sending template '{}'",
+                    metadata.sender_phone, template
+                ),
+            );
+            template_payload(
+                &metadata.sender_phone,
+                &template,
+                &language,
+                &response.content,
+            )
+        };
 
         let payload_bytes = serde_json::to_vec(&payload)
             .map_err(|e| format!("Failed to serialize payload: {}", e))?;
@@ -476,6 +610,13 @@ impl Guest for WhatsAppChannel {
 
     fn on_status(_update: StatusUpdate) {}
 
+    fn on_approval(_request: ApprovalRequest) -> Result<(), String> {
+        // No interactive buttons yet; the host sends a text prompt instead.
+        Err("interactive approvals not supported".to_string())
+    }
+
+    fn on_callback(_payload: String) {}
+
     fn on_shutdown() {
         channel_host::log(
             channel_host::LogLevel::Info,
@@ -597,14 +738,10 @@ fn handle_incoming_message(req: &IncomingHttpRequest) -> OutgoingHttpResponse {
                 })
                 .collect();
 
-            // Skip status updates (delivered, read, etc.) - we only want messages
-            // This prevents loops and unnecessary processing
-            if !value.statuses.is_empty() && value.messages.is_empty() {
-                channel_host::log(
-                    channel_host::LogLevel::Debug,
-                    &format!("Skipping {} status updates", value.statuses.len()),
-                );
-                continue;
+            // Status updates (sent, delivered, read, failed) are logged and
+            // never emitted to the agent, which prevents reply loops.
+            for status in &value.statuses {
+                handle_status(status);
             }
 
             // Process messages
@@ -624,34 +761,37 @@ fn handle_message(
     phone_number_id: &str,
     contact_names: &std::collections::HashMap<String, String>,
 ) {
-    // Only handle text messages for now
-    // TODO: Add support for image, audio, video, document, etc.
-    if message.message_type != "text" {
+    if message.message_type != "text" && message.media().is_none() {
         channel_host::log(
             channel_host::LogLevel::Debug,
-            &format!("Skipping non-text message type: {}", message.message_type),
+            &format!(
+                "Skipping unsupported message type: {}",
+                message.message_type
+            ),
         );
         return;
     }
 
-    // Extract text content
-    let text = match &message.text {
-        Some(t) if !t.body.is_empty() => t.body.clone(),
-        _ => return,
-    };
-
     // Look up sender's name from contacts
     let user_name = contact_names.get(&message.from).cloned();
 
     // Permission check (WhatsApp is always DM)
-    if !check_sender_permission(
-        &message.from,
-        user_name.as_deref(),
-        phone_number_id,
-    ) {
+    if !check_sender_permission(&message.from, user_name.as_deref(), phone_number_id) {
         return;
     }
 
+    // Any inbound message (re)opens the 24h customer service window
+    let _ = channel_host::state_set(
+        &format!("{}{}", LAST_INBOUND_PREFIX, message.from),
+        &message.timestamp,
+    );
+
+    let text = match (&message.text, message.media()) {
+        (Some(t), _) if !t.body.is_empty() => t.body.clone(),
+        (_, Some(media)) => ingest_media(message, media, user_name.as_deref()),
+        _ => return,
+    };
+
     // Build metadata for response routing
     // This is critical - the response handler uses this to know where to send
     let metadata = WhatsAppMessageMetadata {
@@ -681,6 +821,308 @@ fn handle_message(
     );
 }
 
+/// Log a delivery status; failures that need action are surfaced as warnings.
+fn handle_status(status: &MessageStatus) {
+    if status.status != "failed" {
+        channel_host::log(
+            channel_host::LogLevel::Debug,
+            &format!(
+                "Message {} to {}: {}",
+                status.id, status.recipient_id, status.status
+            ),
+        );
+        return;
+    }
+
+    for error in &status.errors {
+        let title = error.title.as_deref().unwrap_or("unknown error");
+        let hint = if error.code == ERROR_REENGAGEMENT {
+            " (24h window closed; configure template_name to reach this user)"
+        } else {
+            ""
+        };
+        channel_host::log(
+            channel_host::LogLevel::Warn,
+            &format!(
+                "Message {} to {} failed: {} (code {}){}",
+                status.id, status.recipient_id, title, error.code, hint
+            ),
+        );
+    }
+}
+
+// ============================================================================
+// Media Ingestion
+// ============================================================================
+
+/// Download a media attachment and save a record of it to the workspace.
+///
+/// Returns the text to emit to the agent: the caption (or a placeholder)
+/// plus the workspace path of the saved record.
+fn ingest_media(
+    message: &WhatsAppMessage,
+    media: &MediaContent,
+    user_name: Option<&str>,
+) -> String {
+    let caption = media
+        .caption
+        .as_deref()
+        .filter(|c| !c.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("[{} received]", message.message_type));
+
+    let download = download_media(&media.id);
+    if let Err(ref e) = download {
+        channel_host::log(
+            channel_host::LogLevel::Warn,
+            &format!("Failed to download media {}: {}", media.id, e),
+        );
+    }
+
+    let path = format!("{}/{}.md", MEDIA_DIR, media_file_stem(&message.id));
+    let transcript = match download.as_ref() {
+        Ok(d) if message.message_type == "audio" => {
+            transcribe_voice_note(message, media, user_name, d, &path)
+        }
+        _ => None,
+    };
+    let caption = match transcript.as_deref() {
+        Some(text) => voice_note_caption(media.caption.as_deref(), text),
+        None => caption,
+    };
+
+    let mut record = media_record(message, media, user_name, download.as_ref().ok());
+    if let Some(ref text) = transcript {
+        record.push_str(&format!("\n## Transcript\n\n{}\n", text));
+    }
+    if let Err(e) = channel_host::workspace_write(&path, &record) {
+        channel_host::log(
+            channel_host::LogLevel::Error,
+            &format!("Failed to save media record {}: {}", path, e),
+        );
+        return caption;
+    }
+
+    format!(
+        "{}\n\n[Attachment saved to channels/whatsapp/{}]",
+        caption, path
+    )
+}
+
+/// Transcribe a downloaded audio message through the host. The host files
+/// the transcript in the active matter, pointing back at the media record.
+fn transcribe_voice_note(
+    message: &WhatsAppMessage,
+    media: &MediaContent,
+    user_name: Option<&str>,
+    download: &DownloadedMedia,
+    record_path: &str,
+) -> Option<String> {
+    let mime_type = download
+        .mime_type
+        .as_deref()
+        .or(media.mime_type.as_deref())
+        .unwrap_or("audio/ogg");
+    let sender = match user_name {
+        Some(name) => format!("{} (+{})", name, message.from),
+        None => format!("+{}", message.from),
+    };
+    let audio_ref = format!("channels/whatsapp/{}", record_path);
+    match channel_host::transcribe_audio(&download.bytes, mime_type, &sender, &audio_ref) {
+        Ok(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+        Ok(_) => None,
+        Err(e) => {
+            channel_host::log(
+                channel_host::LogLevel::Warn,
+                &format!("Voice note {} not transcribed: {}", message.id, e),
+            );
+            None
+        }
+    }
+}
+
+/// Text emitted to the agent for a transcribed voice note.
+fn voice_note_caption(caption: Option<&str>, transcript: &str) -> String {
+    match caption.filter(|c| !c.is_empty()) {
+        Some(caption) => format!("{}\n\n[Voice note] {}", caption, transcript),
+        None => format!("[Voice note] {}", transcript),
+    }
+}
+
+/// Downloaded media file.
+struct DownloadedMedia {
+    mime_type: Option<String>,
+    bytes: Vec<u8>,
+}
+
+/// Resolve a media ID to its URL and fetch the bytes.
+fn download_media(media_id: &str) -> Result<DownloadedMedia, String> {
+    let api_version = channel_host::workspace_read("channels/whatsapp/api_version")
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "v18.0".to_string());
+    let headers = serde_json::json!({
+        "Authorization": "Bearer {WHATSAPP_ACCESS_TOKEN}"
+    })
+    .to_string();
+
+    let lookup_url = format!("https://graph.facebook.com/{}/{}", api_version, media_id);
+    let lookup = channel_host::http_request("GET", &lookup_url, &headers, None, None)?;
+    if lookup.status != 200 {
+        return Err(format!("media lookup returned HTTP {}", lookup.status));
+    }
+    let info: MediaUrlResponse = serde_json::from_slice(&lookup.body)
+        .map_err(|e| format!("invalid media lookup response: {}", e))?;
+    if info.file_size.is_some_and(|size| size > MAX_MEDIA_BYTES) {
+        return Err(format!(
+            "media is {} bytes (max {})",
+            info.file_size.unwrap_or_default(),
+            MAX_MEDIA_BYTES
+        ));
+    }
+
+    let file = channel_host::http_request("GET", &info.url, &headers, None, Some(60_000))?;
+    if file.status != 200 {
+        return Err(format!("media download returned HTTP {}", file.status));
+    }
+    Ok(DownloadedMedia {
+        mime_type: info.mime_type,
+        bytes: file.body,
+    })
+}
+
+/// Build the markdown record saved for a media attachment.
+///
+/// Text documents are stored inline; binary media is described by its
+/// metadata so the agent can reference it.
+fn media_record(
+    message: &WhatsAppMessage,
+    media: &MediaContent,
+    user_name: Option<&str>,
+    download: Option<&DownloadedMedia>,
+) -> String {
+    let mime_type = download
+        .and_then(|d| d.mime_type.as_deref())
+        .or(media.mime_type.as_deref())
+        .unwrap_or("application/octet-stream");
+
+    let mut record = format!(
+        "# WhatsApp {} from {}\n\n",
+        message.message_type, message.from
+    );
+    record.push_str(&format!("- Message ID: {}\n", message.id));
+    if let Some(name) = user_name {
+        record.push_str(&format!("- Sender name: {}\n", name));
+    }
+    record.push_str(&format!("- Received (unix): {}\n", message.timestamp));
+    record.push_str(&format!("- Media ID: {}\n", media.id));
+    record.push_str(&format!("- MIME type: {}\n", mime_type));
+    if let Some(ref filename) = media.filename {
+        record.push_str(&format!("- File name: {}\n", filename));
+    }
+    if let Some(ref sha256) = media.sha256 {
+        record.push_str(&format!("- SHA-256: {}\n", sha256));
+    }
+    if let Some(caption) = media.caption.as_deref().filter(|c| !c.is_empty()) {
+        record.push_str(&format!("- Caption: {}\n", caption));
+    }
+
+    match download {
+        Some(d) => {
+            record.push_str(&format!("- Size: {} bytes\n", d.bytes.len()));
+            let inline = is_text_mime(mime_type) && d.bytes.len() <= MAX_INLINE_TEXT_BYTES;
+            match std::str::from_utf8(&d.bytes) {
+                Ok(text) if inline => {
+                    record.push_str("\n## Content\n\n");
+                    record.push_str(text);
+                    record.push('\n');
+                }
+                _ => {}
+            }
+        }
+        None => record.push_str("- Download: failed (media can be re-fetched by ID for 30 days)\n"),
+    }
+
+    record
+}
+
+/// Whether a MIME type is plain text that can be stored inline.
+fn is_text_mime(mime_type: &str) -> bool {
+    let base = mime_type.split(';').next().unwrap_or("").trim();
+    base.starts_with("text/") || matches!(base, "application/json" | "application/xml")
+}
+
+/// File-safe stem for a WhatsApp message ID (e.g. "wamid.HBg...=").
+fn media_file_stem(message_id: &str) -> String {
+    message_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// Outbound Payloads
+// ============================================================================
+
+/// Latest known inbound time for a sender: the stored value or the
+/// timestamp of the message being answered, whichever is newer.
+fn last_inbound_secs(sender_phone: &str, message_timestamp: &str) -> u64 {
+    let stored = channel_host::state_get(&format!("{}{}", LAST_INBOUND_PREFIX, sender_phone))
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(0);
+    stored.max(message_timestamp.parse::<u64>().unwrap_or(0))
+}
+
+/// Whether a free-form reply is still allowed.
+fn within_service_window(last_inbound_secs: u64, now_secs: u64) -> bool {
+    now_secs.saturating_sub(last_inbound_secs) + SERVICE_WINDOW_MARGIN_SECS < SERVICE_WINDOW_SECS
+}
+
+/// Payload for a free-form text reply.
+fn text_payload(to: &str, body: &str) -> serde_json::Value {
+    serde_json::json!({
+        "messaging_product": "whatsapp",
+        "recipient_type": "individual",
+        "to": to,
+        "type": "text",
+        "text": {
+            "preview_url": false,
+            "body": body
+        }
+    })
+}
+
+/// Payload for a template message carrying the reply as its body parameter.
+fn template_payload(to: &str, template: &str, language: &str, body: &str) -> serde_json::Value {
+    let mut param: String = body.chars().take(MAX_TEMPLATE_PARAM_CHARS).collect();
+    if param.len() < body.len() {
+        param.truncate(param.len() - param.chars().last().map_or(0, char::len_utf8));
+        param.push('…');
+    }
+    // Template parameters may not contain newlines or tabs
+    let param = param.replace(['\n', '\t'], " ");
+
+    serde_json::json!({
+        "messaging_product": "whatsapp",
+        "recipient_type": "individual",
+        "to": to,
+        "type": "template",
+        "template": {
+            "name": template,
+            "language": { "code": language },
+            "components": [{
+                "type": "body",
+                "parameters": [{ "type": "text", "text": param }]
+            }]
+        }
+    })
+}
+
 // ============================================================================
 // Utilities
 // ============================================================================
@@ -750,10 +1192,7 @@ fn check_sender_permission(
             Ok(result) => {
                 channel_host::log(
                     channel_host::LogLevel::Info,
-                    &format!(
-                        "Pairing request for {}: code {}",
-                        sender_phone, result.code
-                    ),
+                    &format!("Pairing request for {}: code {}", sender_phone, result.code),
                 );
                 if result.created {
                     let _ = send_pairing_reply(sender_phone, phone_number_id, &result.code);
@@ -947,4 +1386,142 @@ mod tests {
         assert_eq!(parsed.phone_number_id, "123456");
         assert_eq!(parsed.sender_phone, "15551234567");
     }
+
+    #[test]
+    fn test_parse_media_message_and_failed_status() {
+        let json = r#"{
+            "object": "whatsapp_business_account",
+            "entry": [{
+                "id": "123456789",
+                "changes": [{
+                    "field": "messages",
+                    "value": {
+                        "messaging_product": "whatsapp",
+                        "metadata": {
+                            "display_phone_number": "+1234567890",
+                            "phone_number_id": "987654321"
+                        },
+                        "messages": [{
+                            "id": "wamid.doc1",
+                            "from": "15551234567",
+                            "timestamp": "1234567890",
+                            "type": "document",
+                            "document": {
+                                "id": "media-42",
+                                "mime_type": "application/pdf",
+                                "sha256": "abc",
+                                "filename": "lease.pdf",
+                                "caption": "Signed lease"
+                            }
+                        }],
+                        "statuses": [{
+                            "id": "wamid.out1",
+                            "status": "failed",
+                            "timestamp": "1234567890",
+                            "recipient_id": "15551234567",
+                            "errors": [{ "code": 131047, "title": "Re-engagement message" }]
+                        }]
+                    }
+                }]
+            }]
+        }"#;
+
+        let payload: WebhookPayload = serde_json::from_str(json).unwrap();
+        let value = &payload.entry[0].changes[0].value;
+
+        let media = value.messages[0].media().unwrap();
+        assert_eq!(media.id, "media-42");
+        assert_eq!(media.filename.as_deref(), Some("lease.pdf"));
+        assert_eq!(media.caption.as_deref(), Some("Signed lease"));
+
+        assert_eq!(value.statuses[0].errors[0].code, ERROR_REENGAGEMENT);
+    }
+
+    #[test]
+    fn test_service_window() {
+        let received = 1_700_000_000;
+        assert!(within_service_window(received, received + 60));
+        assert!(within_service_window(received, received + 23 * 3600));
+        assert!(!within_service_window(
+            received,
+            received + SERVICE_WINDOW_SECS
+        ));
+        assert!(!within_service_window(0, received));
+    }
+
+    #[test]
+    fn test_template_payload_truncates_and_flattens() {
+        let body = format!("line one\nline two {}", "x".repeat(2000));
+        let payload = template_payload("15551234567", "case_update", "en_US", &body);
+
+        assert_eq!(payload["type"], "template");
+        assert_eq!(payload["template"]["name"], "case_update");
+        assert_eq!(payload["template"]["language"]["code"], "en_US");
+
+        let param = payload["template"]["components"][0]["parameters"][0]["text"]
+            .as_str()
+            .unwrap();
+        assert_eq!(param.chars().count(), MAX_TEMPLATE_PARAM_CHARS);
+        assert!(param.starts_with("line one line two"));
+        assert!(param.ends_with('…'));
+
+        let short = template_payload("1", "t", "en_US", "hi");
+        assert_eq!(
+            short["template"]["components"][0]["parameters"][0]["text"],
+            "hi"
+        );
+    }
+
+    #[test]
+    fn test_voice_note_caption() {
+        assert_eq!(
+            voice_note_caption(None, "Please call me."),
+            "[Voice note] Please call me."
+        );
+        assert_eq!(
+            voice_note_caption(Some(""), "Please call me."),
+            "[Voice note] Please call me."
+        );
+        assert_eq!(
+            voice_note_caption(Some("re: lease"), "Please call me."),
+            "re: lease\n\n[Voice note] Please call me."
+        );
+    }
+
+    #[test]
+    fn test_media_record() {
+        let message: WhatsAppMessage = serde_json::from_str(
+            r#"{
+                "id": "wamid.txt1",
+                "from": "15551234567",
+                "timestamp": "1234567890",
+                "type": "document",
+                "document": { "id": "media-7", "mime_type": "text/plain", "filename": "notes.txt" }
+            }"#,
+        )
+        .unwrap();
+        let media = message.media().unwrap();
+
+        let download = DownloadedMedia {
+            mime_type: Some("text/plain".to_string()),
+            bytes: b"Meeting at 10am".to_vec(),
+        };
+        let record = media_record(&message, media, Some("John Doe"), Some(&download));
+        assert!(record.contains("- File name: notes.txt"));
+        assert!(record.contains("- Sender name: John Doe"));
+        assert!(record.contains("## Content\n\nMeeting at 10am"));
+
+        let binary = DownloadedMedia {
+            mime_type: Some("image/jpeg".to_string()),
+            bytes: vec![0xff, 0xd8, 0xff],
+        };
+        let record = media_record(&message, media, None, Some(&binary));
+        assert!(record.contains("- MIME type: image/jpeg"));
+        assert!(!record.contains("## Content"));
+
+        let record = media_record(&message, media, None, None);
+        assert!(record.contains("- Download: failed"));
+
+        assert_eq!(media_file_stem("wamid.HBg=="), "wamid_HBg__");
+    }
 }
@@ -0,0 +1,28 @@
+-- Phase 3: Entity extraction review queue (V22)
+--
+-- Person/organization names extracted from ingested matter documents are
+-- queued here with provenance until an attorney accepts them into the
+-- conflict graph (matter_parties) or rejects them.
+
+CREATE TABLE IF NOT EXISTS party_candidates (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    matter_id       TEXT NOT NULL,
+    name            TEXT NOT NULL,
+    name_normalized TEXT NOT NULL,
+    entity_type     TEXT NOT NULL CHECK (entity_type IN ('person', 'organization')),
+    source_path     TEXT NOT NULL,
+    snippet         TEXT,
+    confidence      DOUBLE PRECISION NOT NULL DEFAULT 0,
+    extractor       TEXT NOT NULL,
+    status          TEXT NOT NULL DEFAULT 'pending'
+                    CHECK (status IN ('pending', 'accepted', 'rejected')),
+    party_id        UUID REFERENCES parties(id) ON DELETE SET NULL,
+    reviewed_by     TEXT,
+    reviewed_at     TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (matter_id, name_normalized, source_path)
+);
+
+CREATE INDEX IF NOT EXISTS idx_party_candidates_matter_status
+    ON party_candidates(matter_id, status, created_at DESC);
@@ -0,0 +1,28 @@
+-- Phase 3: Foreign-language exhibit translations (V23)
+--
+-- Machine translations are stored in the workspace beside their source
+-- document. This table tracks provenance and the certification workflow
+-- until a certified human translation supersedes the machine draft.
+
+CREATE TABLE IF NOT EXISTS document_translations (
+    id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id          TEXT NOT NULL,
+    matter_id        TEXT NOT NULL,
+    source_path      TEXT NOT NULL,
+    source_language  TEXT,
+    target_language  TEXT NOT NULL,
+    translation_path TEXT NOT NULL,
+    provider         TEXT NOT NULL,
+    status           TEXT NOT NULL DEFAULT 'machine_draft'
+                     CHECK (status IN ('machine_draft', 'certification_requested', 'certified')),
+    translator       TEXT,
+    certified_path   TEXT,
+    certified_at     TIMESTAMPTZ,
+    created_by       TEXT NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, matter_id, source_path, target_language)
+);
+
+CREATE INDEX IF NOT EXISTS idx_document_translations_user_matter
+    ON document_translations(user_id, matter_id, updated_at DESC);
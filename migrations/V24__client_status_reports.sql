@@ -0,0 +1,27 @@
+-- Phase 3: Client status reports (V24)
+--
+-- Drafted client-facing status updates wait here for attorney approval
+-- before the email tool delivers them.
+
+CREATE TABLE IF NOT EXISTS client_status_reports (
+    id             UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id        TEXT NOT NULL,
+    matter_id      TEXT NOT NULL,
+    report_path    TEXT NOT NULL,
+    subject        TEXT NOT NULL,
+    recipient      TEXT,
+    status         TEXT NOT NULL DEFAULT 'pending_approval'
+                   CHECK (status IN ('pending_approval', 'approved', 'rejected', 'sent')),
+    reviewed_by    TEXT,
+    review_note    TEXT,
+    reviewed_at    TIMESTAMPTZ,
+    sent_at        TIMESTAMPTZ,
+    delivery_error TEXT,
+    created_by     TEXT NOT NULL,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, matter_id, report_path)
+);
+
+CREATE INDEX IF NOT EXISTS idx_client_status_reports_user_matter
+    ON client_status_reports(user_id, matter_id, created_at DESC);
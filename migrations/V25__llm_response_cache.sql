@@ -0,0 +1,17 @@
+-- Phase 3: Persistent LLM response cache (V25)
+--
+-- Completions keyed by a SHA-256 hash of the normalized model, system
+-- prompt, messages, and tool schema, so repeated routine runs over an
+-- identical context are served without another provider call.
+
+CREATE TABLE IF NOT EXISTS llm_response_cache (
+    cache_key  TEXT PRIMARY KEY,
+    model      TEXT NOT NULL,
+    response   JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    hit_count  BIGINT NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_llm_response_cache_expires
+    ON llm_response_cache(expires_at);
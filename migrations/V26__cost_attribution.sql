@@ -0,0 +1,35 @@
+-- Phase 3: Per-matter cost attribution (V26)
+--
+-- Every LLM call, tool execution, and sandbox job LLM call is written to
+-- the cost ledger with the active matter and its client, so spend can be
+-- reported per matter and capped. Routines marked urgent keep running
+-- when their matter's monthly cap is exhausted.
+
+CREATE TABLE IF NOT EXISTS cost_entries (
+    id            UUID PRIMARY KEY,
+    user_id       TEXT NOT NULL,
+    matter_id     TEXT,
+    client_id     UUID,
+    source        TEXT NOT NULL CHECK (source IN ('llm_call', 'tool_execution', 'sandbox_job')),
+    job_id        UUID,
+    label         TEXT NOT NULL,
+    input_tokens  BIGINT NOT NULL DEFAULT 0,
+    output_tokens BIGINT NOT NULL DEFAULT 0,
+    cost          NUMERIC(20, 10) NOT NULL,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_cost_entries_user_created
+    ON cost_entries(user_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_cost_entries_user_matter_created
+    ON cost_entries(user_id, matter_id, created_at);
+
+CREATE TABLE IF NOT EXISTS matter_spend_caps (
+    user_id           TEXT NOT NULL,
+    matter_id         TEXT NOT NULL,
+    monthly_cap_cents BIGINT NOT NULL CHECK (monthly_cap_cents >= 0),
+    updated_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, matter_id)
+);
+
+ALTER TABLE routines ADD COLUMN IF NOT EXISTS urgent BOOLEAN NOT NULL DEFAULT FALSE;
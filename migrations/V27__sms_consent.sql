@@ -0,0 +1,21 @@
+-- SMS consent records (V27)
+--
+-- One row per phone number that has texted an opt-in or opt-out keyword
+-- (START/STOP and their synonyms) to the firm's Twilio number. Proactive
+-- SMS such as deadline reminders are only sent to opted-in numbers, and
+-- nothing is sent to opted-out numbers. client_id links the number to a
+-- client record when the number matches the client's phone.
+
+CREATE TABLE IF NOT EXISTS sms_consents (
+    user_id      TEXT NOT NULL,
+    phone_number TEXT NOT NULL,
+    client_id    UUID REFERENCES clients(id) ON DELETE SET NULL,
+    status       TEXT NOT NULL CHECK (status IN ('opted_in', 'opted_out')),
+    source       TEXT NOT NULL,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, phone_number)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sms_consents_user_client
+    ON sms_consents(user_id, client_id);
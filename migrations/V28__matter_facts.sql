@@ -0,0 +1,28 @@
+-- Matter chronology facts (V28)
+--
+-- Dated facts extracted from matter documents, each citing the document
+-- (and line) it came from. The matter's facts/key_facts.md and
+-- templates/chronology.md tables are regenerated from these rows, so
+-- re-extracting a document replaces its facts rather than appending.
+
+CREATE TABLE IF NOT EXISTS matter_facts (
+    id             UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id        TEXT NOT NULL,
+    matter_id      TEXT NOT NULL,
+    fact_date      DATE NOT NULL,
+    description    TEXT NOT NULL,
+    source_path    TEXT NOT NULL,
+    source_locator TEXT,
+    source_excerpt TEXT,
+    confidence     DOUBLE PRECISION NOT NULL DEFAULT 0
+                   CHECK (confidence >= 0 AND confidence <= 1),
+    extractor      TEXT NOT NULL,
+    created_by     TEXT NOT NULL,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_matter_facts_user_matter_date
+    ON matter_facts(user_id, matter_id, fact_date);
+CREATE INDEX IF NOT EXISTS idx_matter_facts_user_matter_source
+    ON matter_facts(user_id, matter_id, source_path);
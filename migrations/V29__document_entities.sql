@@ -0,0 +1,26 @@
+-- Document entity mentions (V29)
+--
+-- People, organizations, dates, and monetary amounts found in matter
+-- documents at ingestion, one row per mention per document. People and
+-- organizations are also queued as party candidates; this table keeps
+-- every mention with its document reference so the matter entity graph
+-- can show where each name, date, and amount appears. Re-ingesting a
+-- document replaces its rows.
+
+CREATE TABLE IF NOT EXISTS document_entities (
+    id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id          TEXT NOT NULL,
+    matter_id        TEXT NOT NULL,
+    kind             TEXT NOT NULL CHECK (kind IN ('person', 'organization', 'date', 'amount')),
+    value            TEXT NOT NULL,
+    normalized_value TEXT NOT NULL,
+    source_path      TEXT NOT NULL,
+    snippet          TEXT,
+    confidence       DOUBLE PRECISION NOT NULL DEFAULT 0,
+    extractor        TEXT NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, matter_id, source_path, kind, normalized_value)
+);
+
+CREATE INDEX IF NOT EXISTS idx_document_entities_user_matter_kind
+    ON document_entities(user_id, matter_id, kind, normalized_value);
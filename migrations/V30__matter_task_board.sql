@@ -0,0 +1,10 @@
+-- Kanban board ordering for matter tasks (V30)
+--
+-- `position` orders cards within a board lane. New tasks are appended to
+-- the end of their status column; existing rows start at 0 and fall back
+-- to creation order.
+
+ALTER TABLE matter_tasks ADD COLUMN IF NOT EXISTS position BIGINT NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_matter_tasks_board
+    ON matter_tasks(user_id, matter_id, status, position);
@@ -0,0 +1,14 @@
+-- Subtasks and checklists for matter tasks (V31)
+--
+-- `parent_task_id` nests a task under another task on the same matter.
+-- There is no foreign key so legal restores can load tasks in any order;
+-- deleting a parent detaches its subtasks in the store.
+-- `checklist` holds ordered `{text, done}` steps.
+
+ALTER TABLE matter_tasks
+    ADD COLUMN IF NOT EXISTS parent_task_id UUID;
+ALTER TABLE matter_tasks
+    ADD COLUMN IF NOT EXISTS checklist JSONB NOT NULL DEFAULT '[]'::jsonb;
+
+CREATE INDEX IF NOT EXISTS idx_matter_tasks_parent
+    ON matter_tasks(user_id, matter_id, parent_task_id);
@@ -0,0 +1,21 @@
+-- Workspace document tags (V32)
+--
+-- Free-form key/value labels on workspace documents, keyed by path, for
+-- document review workflows that cut across the folder structure (hot-doc,
+-- responsive, privileged, ...). A key appears at most once per document;
+-- tags without a value are plain flags.
+
+CREATE TABLE IF NOT EXISTS document_tags (
+    id         UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id    TEXT NOT NULL,
+    path       TEXT NOT NULL,
+    key        TEXT NOT NULL,
+    value      TEXT,
+    created_by TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, path, key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_document_tags_user_key_value
+    ON document_tags(user_id, key, value);
@@ -0,0 +1,22 @@
+-- Workspace trash (V33)
+--
+-- Deleting a workspace document moves it here instead of dropping it, so an
+-- accidental deletion can be restored. Content is kept exactly as stored
+-- (matter files stay encrypted). Rows are purged explicitly or once they
+-- pass the trash retention window.
+
+CREATE TABLE IF NOT EXISTS memory_trash (
+    id                 UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id            TEXT NOT NULL,
+    agent_id           UUID,
+    path               TEXT NOT NULL,
+    content            TEXT NOT NULL,
+    metadata           JSONB NOT NULL DEFAULT '{}',
+    deleted_by         TEXT NOT NULL,
+    document_created_at TIMESTAMPTZ NOT NULL,
+    document_updated_at TIMESTAMPTZ NOT NULL,
+    deleted_at         TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_memory_trash_user_deleted
+    ON memory_trash(user_id, deleted_at DESC);
@@ -0,0 +1,17 @@
+-- Workspace document history (V34)
+--
+-- Every content update records a revision with the stored content, its
+-- SHA-256, and a line diff against the previous revision (NULL when the
+-- content is encrypted). Restoring a revision writes it back as a new
+-- revision, so history is never rewritten.
+
+CREATE TABLE IF NOT EXISTS memory_document_revisions (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    document_id  UUID NOT NULL REFERENCES memory_documents(id) ON DELETE CASCADE,
+    revision     INTEGER NOT NULL,
+    content      TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    diff         TEXT,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (document_id, revision)
+);
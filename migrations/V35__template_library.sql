@@ -0,0 +1,44 @@
+-- Firm-wide template library (V35)
+--
+-- Shared (non-matter) templates carry practice-area and jurisdiction
+-- categories, an immutable version history, and a usage log written each
+-- time a template generates a matter document.
+
+CREATE TABLE IF NOT EXISTS document_template_library (
+    template_id   UUID PRIMARY KEY REFERENCES document_templates(id) ON DELETE CASCADE,
+    user_id       TEXT NOT NULL,
+    practice_area TEXT,
+    jurisdiction  TEXT,
+    description   TEXT,
+    promoted_from UUID,
+    updated_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_document_template_library_user
+    ON document_template_library(user_id, practice_area, jurisdiction);
+
+CREATE TABLE IF NOT EXISTS document_template_versions (
+    id             UUID PRIMARY KEY,
+    template_id    UUID NOT NULL REFERENCES document_templates(id) ON DELETE CASCADE,
+    user_id        TEXT NOT NULL,
+    version        INTEGER NOT NULL,
+    name           TEXT NOT NULL,
+    body           TEXT NOT NULL,
+    variables_json JSONB NOT NULL DEFAULT '[]'::jsonb,
+    created_by     TEXT NOT NULL,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (template_id, version)
+);
+
+CREATE TABLE IF NOT EXISTS document_template_uses (
+    id            UUID PRIMARY KEY,
+    user_id       TEXT NOT NULL,
+    template_id   UUID NOT NULL REFERENCES document_templates(id) ON DELETE CASCADE,
+    matter_id     TEXT NOT NULL,
+    document_path TEXT NOT NULL,
+    used_by       TEXT NOT NULL,
+    used_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_document_template_uses_user_used_at
+    ON document_template_uses(user_id, used_at DESC);
@@ -0,0 +1,3 @@
+-- Scheduling priority of a sandbox job (routine, normal, interactive).
+-- Restarts reuse the stored value so a restarted job keeps its place.
+ALTER TABLE agent_jobs ADD COLUMN IF NOT EXISTS priority TEXT NOT NULL DEFAULT 'normal';
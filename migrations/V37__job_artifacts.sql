@@ -0,0 +1,15 @@
+-- Files produced by sandbox jobs, copied out of the job's project directory
+-- when it finishes. Rows past the retention window are swept together with
+-- their stored copies.
+CREATE TABLE IF NOT EXISTS job_artifacts (
+    job_id UUID NOT NULL REFERENCES agent_jobs(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    content_type TEXT NOT NULL,
+    storage_path TEXT NOT NULL,
+    promoted_to TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (job_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_artifacts_created ON job_artifacts(created_at);
@@ -0,0 +1,4 @@
+-- Per-routine override of the firm's execution windows (see the
+-- `execution_policy` setting): replacement windows and blackout opt-out.
+ALTER TABLE routines
+    ADD COLUMN IF NOT EXISTS execution_override JSONB NOT NULL DEFAULT '{}'::jsonb;
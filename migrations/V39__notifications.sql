@@ -0,0 +1,20 @@
+-- Notification inbox: routine outputs, deadline alerts, and system warnings
+-- addressed to a user. Rows stay until acknowledged; a snoozed row drops out
+-- of the unread count until `snoozed_until` passes.
+CREATE TABLE IF NOT EXISTS notifications (
+    id            UUID PRIMARY KEY,
+    user_id       TEXT NOT NULL,
+    kind          TEXT NOT NULL CHECK (kind IN ('routine', 'deadline', 'system')),
+    severity      TEXT NOT NULL DEFAULT 'info' CHECK (severity IN ('info', 'warn', 'critical')),
+    title         TEXT NOT NULL,
+    body          TEXT NOT NULL DEFAULT '',
+    matter_id     TEXT,
+    source        TEXT,
+    read_at       TIMESTAMPTZ,
+    acked_at      TIMESTAMPTZ,
+    snoozed_until TIMESTAMPTZ,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_notifications_user_created
+    ON notifications(user_id, created_at DESC);
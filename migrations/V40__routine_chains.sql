@@ -0,0 +1,4 @@
+-- Follow-up routines fired when a routine finishes: `on_success` names and
+-- `on_condition` rules matched against the run's status and summary.
+ALTER TABLE routines
+    ADD COLUMN IF NOT EXISTS chain JSONB NOT NULL DEFAULT '{}'::jsonb;
@@ -0,0 +1,22 @@
+-- External share links for workspace documents and filing packages. Only a
+-- hash of the URL token is stored; downloads bump the counter and are also
+-- written to the legal audit log.
+CREATE TABLE IF NOT EXISTS document_share_links (
+    id                 UUID PRIMARY KEY,
+    user_id            TEXT NOT NULL,
+    path               TEXT NOT NULL,
+    matter_id          TEXT,
+    token_hash         TEXT NOT NULL UNIQUE,
+    password_hash      TEXT,
+    recipient          TEXT,
+    watermark          BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by         TEXT NOT NULL,
+    expires_at         TIMESTAMPTZ NOT NULL,
+    revoked_at         TIMESTAMPTZ,
+    download_count     BIGINT NOT NULL DEFAULT 0,
+    last_downloaded_at TIMESTAMPTZ,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_document_share_links_user_path
+    ON document_share_links(user_id, path);
@@ -0,0 +1,19 @@
+-- Note threads and @mention notifications (V43)
+--
+-- `parent_id` makes a matter note a reply in the thread rooted at another
+-- note on the same matter. Threads are one level deep: replies to a reply
+-- attach to its root. There is no foreign key so legal restores can load
+-- notes in any order; deleting a root note deletes its replies in the store.
+--
+-- Notifications gain a `mention` kind for teammates named in a note.
+
+ALTER TABLE matter_notes
+    ADD COLUMN IF NOT EXISTS parent_id UUID;
+
+CREATE INDEX IF NOT EXISTS idx_matter_notes_parent
+    ON matter_notes(user_id, matter_id, parent_id);
+
+ALTER TABLE notifications DROP CONSTRAINT IF EXISTS notifications_kind_check;
+ALTER TABLE notifications
+    ADD CONSTRAINT notifications_kind_check
+    CHECK (kind IN ('routine', 'deadline', 'system', 'mention'));
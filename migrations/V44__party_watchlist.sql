@@ -0,0 +1,15 @@
+-- Adverse-party watchlist. New clients and matters are screened against
+-- these names, and ingested documents are scanned for them; matches are
+-- audited as `watchlist_match` and posted to the notification inbox.
+CREATE TABLE IF NOT EXISTS party_watchlist (
+    id              UUID PRIMARY KEY,
+    user_id         TEXT NOT NULL,
+    name            TEXT NOT NULL,
+    name_normalized TEXT NOT NULL,
+    category        TEXT NOT NULL DEFAULT 'adverse_party'
+                    CHECK (category IN ('adverse_party', 'sanctioned', 'other')),
+    reason          TEXT,
+    created_by      TEXT NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, name_normalized)
+);
@@ -0,0 +1,24 @@
+-- Matter exhibit register (V45)
+--
+-- Exhibits are numbered per party prefix (`P-1`, `P-2`, `D-1`) in the order
+-- they are marked. Numbers are never reused: withdrawing an exhibit keeps its
+-- row so later exhibits and regenerated exhibit lists keep their labels.
+CREATE TABLE IF NOT EXISTS matter_exhibits (
+    id                 UUID PRIMARY KEY,
+    user_id            TEXT NOT NULL,
+    matter_id          TEXT NOT NULL,
+    party_prefix       TEXT NOT NULL,
+    sequence           INTEGER NOT NULL CHECK (sequence > 0),
+    label              TEXT NOT NULL,
+    path               TEXT NOT NULL,
+    matter_document_id UUID,
+    description        TEXT,
+    status             TEXT NOT NULL DEFAULT 'marked'
+                       CHECK (status IN ('marked', 'withdrawn')),
+    created_by         TEXT NOT NULL,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    withdrawn_at       TIMESTAMPTZ,
+    FOREIGN KEY (user_id, matter_id) REFERENCES matters(user_id, matter_id) ON DELETE CASCADE,
+    UNIQUE (user_id, matter_id, party_prefix, sequence),
+    UNIQUE (user_id, matter_id, label)
+);
@@ -0,0 +1,46 @@
+-- Settlement negotiations (V46)
+--
+-- Every demand and offer exchanged on a matter is kept in order so the
+-- negotiation history can be reproduced for mediation. Client settlement
+-- authority is recorded separately: each grant is a new row and the latest
+-- one (by grant date) is the authority in force.
+CREATE TABLE IF NOT EXISTS matter_settlement_proposals (
+    id             UUID PRIMARY KEY,
+    user_id        TEXT NOT NULL,
+    matter_id      TEXT NOT NULL,
+    kind           TEXT NOT NULL CHECK (kind IN ('demand', 'offer')),
+    side           TEXT NOT NULL CHECK (side IN ('client', 'opposing')),
+    from_party     TEXT NOT NULL,
+    to_party       TEXT,
+    amount         NUMERIC(14,2) CHECK (amount IS NULL OR amount > 0),
+    terms          TEXT,
+    proposed_on    DATE NOT NULL,
+    expires_on     DATE,
+    status         TEXT NOT NULL DEFAULT 'open'
+                   CHECK (status IN ('open', 'accepted', 'rejected', 'countered', 'withdrawn', 'expired')),
+    response_notes TEXT,
+    created_by     TEXT NOT NULL,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_id, matter_id) REFERENCES matters(user_id, matter_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_matter_settlement_proposals_matter
+    ON matter_settlement_proposals(user_id, matter_id, proposed_on);
+
+CREATE TABLE IF NOT EXISTS matter_settlement_authority (
+    id         UUID PRIMARY KEY,
+    user_id    TEXT NOT NULL,
+    matter_id  TEXT NOT NULL,
+    kind       TEXT NOT NULL CHECK (kind IN ('minimum', 'maximum')),
+    amount     NUMERIC(14,2) NOT NULL CHECK (amount > 0),
+    granted_on DATE NOT NULL,
+    granted_by TEXT,
+    notes      TEXT,
+    created_by TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_id, matter_id) REFERENCES matters(user_id, matter_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_matter_settlement_authority_matter
+    ON matter_settlement_authority(user_id, matter_id, granted_on DESC);
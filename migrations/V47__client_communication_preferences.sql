@@ -0,0 +1,35 @@
+-- Client communication preferences and consent (V47)
+--
+-- Preferences hold one row per client: the channels the client may be
+-- contacted on, quiet hours, the language to write in, and disclaimers every
+-- client-facing message must carry. Consent decisions are append-only; the
+-- latest decision per channel is the one in force, and earlier ones remain
+-- as the compliance record.
+CREATE TABLE IF NOT EXISTS client_communication_preferences (
+    user_id          TEXT NOT NULL,
+    client_id        UUID NOT NULL REFERENCES clients(id) ON DELETE CASCADE,
+    allowed_channels JSONB NOT NULL DEFAULT '[]'::jsonb,
+    quiet_hours      JSONB NOT NULL DEFAULT '[]'::jsonb,
+    timezone         TEXT,
+    language         TEXT,
+    disclaimers      JSONB NOT NULL DEFAULT '[]'::jsonb,
+    updated_by       TEXT NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, client_id)
+);
+
+CREATE TABLE IF NOT EXISTS client_communication_consents (
+    id           UUID PRIMARY KEY,
+    user_id      TEXT NOT NULL,
+    client_id    UUID NOT NULL REFERENCES clients(id) ON DELETE CASCADE,
+    channel      TEXT NOT NULL CHECK (channel IN ('email', 'sms', 'phone', 'mail')),
+    status       TEXT NOT NULL CHECK (status IN ('granted', 'revoked')),
+    source       TEXT NOT NULL,
+    consented_at TIMESTAMPTZ NOT NULL,
+    recorded_by  TEXT NOT NULL,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_client_communication_consents_client
+    ON client_communication_consents(user_id, client_id, consented_at DESC);
@@ -0,0 +1,24 @@
+-- Firm precedent bank (V48)
+--
+-- Work product from closed matters is anonymized and copied under
+-- `precedent/` in the workspace. Each row records where the copy came from:
+-- the source matter is kept for attribution and ethical-wall checks but is
+-- not written into the precedent file. Rows outlive the source matter so a
+-- deleted matter still screens its precedent instead of orphaning it.
+CREATE TABLE IF NOT EXISTS precedents (
+    id               UUID PRIMARY KEY,
+    user_id          TEXT NOT NULL,
+    title            TEXT NOT NULL,
+    path             TEXT NOT NULL,
+    source_matter_id TEXT NOT NULL,
+    source_path      TEXT NOT NULL,
+    document_type    TEXT,
+    practice_area    TEXT,
+    jurisdiction     TEXT,
+    promoted_by      TEXT NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, path)
+);
+
+CREATE INDEX IF NOT EXISTS idx_precedents_source_matter
+    ON precedents(user_id, source_matter_id);
@@ -0,0 +1,8 @@
+-- Share link password backoff (V49)
+--
+-- Wrong passwords are counted per link, with the time of the latest one, so
+-- the gateway can make further guesses wait longer; a successful download
+-- resets the count.
+ALTER TABLE document_share_links
+    ADD COLUMN IF NOT EXISTS failed_password_attempts BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS last_password_failure_at TIMESTAMPTZ;
@@ -20,7 +20,7 @@ use crate::agent::submission::{Submission, SubmissionParser, SubmissionResult};
 use crate::agent::{HeartbeatConfig as AgentHeartbeatConfig, Router, Scheduler};
 use crate::channels::{ChannelManager, IncomingMessage, OutgoingResponse, StatusUpdate};
 use crate::config::{AgentConfig, HeartbeatConfig, RoutineConfig, SkillsConfig};
-use crate::context::ContextManager;
+use crate::context::{ContextManager, JobContext};
 use crate::db::Database;
 use crate::error::Error;
 use crate::extensions::ExtensionManager;
@@ -74,6 +74,8 @@ pub struct AgentDeps {
     pub hooks: Arc<HookRegistry>,
     /// Cost enforcement guardrails (daily budget, hourly rate limits).
     pub cost_guard: Arc<crate::agent::cost_guard::CostGuard>,
+    /// Filled with the routine engine once it starts, for webhook triggers.
+    pub routine_engine_slot: Option<crate::agent::routine_engine::RoutineEngineSlot>,
 }
 
 /// The main agent that coordinates all components.
@@ -116,16 +118,19 @@ impl Agent {
 
         let session_manager = session_manager.unwrap_or_else(|| Arc::new(SessionManager::new()));
 
-        let scheduler = Arc::new(Scheduler::new(
-            config.clone(),
-            crate::legal::policy::is_max_lockdown(&deps.legal_config),
-            context_manager.clone(),
-            deps.llm.clone(),
-            deps.safety.clone(),
-            deps.tools.clone(),
-            deps.store.clone(),
-            deps.hooks.clone(),
-        ));
+        let scheduler = Arc::new(
+            Scheduler::new(
+                config.clone(),
+                crate::legal::policy::is_max_lockdown(&deps.legal_config),
+                context_manager.clone(),
+                deps.llm.clone(),
+                deps.safety.clone(),
+                deps.tools.clone(),
+                deps.store.clone(),
+                deps.hooks.clone(),
+            )
+            .with_cost_guard(deps.cost_guard.clone()),
+        );
 
         Self {
             config,
@@ -204,6 +209,35 @@ impl Agent {
         legal
     }
 
+    /// Build the job context used for interactive chat tool calls.
+    ///
+    /// Carries the effective active matter in metadata so matter-aware tools
+    /// (e.g. `memory_search`) scope themselves to the session's matter, and
+    /// marks sandbox jobs started from chat as interactive.
+    pub(super) fn chat_job_context(
+        message: &IncomingMessage,
+        legal: &crate::config::LegalConfig,
+    ) -> JobContext {
+        let mut job_ctx =
+            JobContext::with_user(&message.user_id, "chat", "Interactive chat session");
+        job_ctx.metadata = serde_json::json!({
+            "active_matter": legal.active_matter,
+            "job_priority": "interactive",
+        });
+        job_ctx
+    }
+
+    /// Chat spend is billed to the sender's active matter.
+    pub(super) fn chat_cost_attribution(
+        message: &IncomingMessage,
+        legal: &crate::config::LegalConfig,
+    ) -> crate::agent::cost_guard::CostAttribution {
+        crate::agent::cost_guard::CostAttribution::new(
+            &message.user_id,
+            legal.active_matter.clone(),
+        )
+    }
+
     pub(super) fn workspace(&self) -> Option<&Arc<Workspace>> {
         self.deps.workspace.as_ref()
     }
@@ -363,6 +397,39 @@ impl Agent {
             }
         });
 
+        // Forward person-addressed notifications (e.g. note @mentions) to
+        // the channel each recipient chose.
+        {
+            let mut deliveries = crate::notifications::subscribe_deliveries();
+            let channels = self.channels.clone();
+            tokio::spawn(async move {
+                loop {
+                    match deliveries.recv().await {
+                        Ok(delivery) => {
+                            if let Err(e) = channels
+                                .broadcast(
+                                    &delivery.channel,
+                                    &delivery.recipient,
+                                    OutgoingResponse::text(delivery.message),
+                                )
+                                .await
+                            {
+                                tracing::warn!(
+                                    "Failed to deliver notification to {}: {}",
+                                    delivery.channel,
+                                    e
+                                );
+                            }
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                            tracing::warn!(skipped, "Notification deliveries lagged");
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            });
+        }
+
         // Spawn heartbeat if enabled
         let heartbeat_handle = if let Some(ref hb_config) = self.heartbeat_config {
             if hb_config.enabled {
@@ -441,14 +508,18 @@ impl Agent {
                     let (notify_tx, mut notify_rx) =
                         tokio::sync::mpsc::channel::<OutgoingResponse>(32);
 
-                    let engine = Arc::new(RoutineEngine::new(
-                        rt_config.clone(),
-                        Arc::clone(store),
-                        self.llm().clone(),
-                        Arc::clone(workspace),
-                        notify_tx,
-                        Some(self.scheduler.clone()),
-                    ));
+                    let engine = Arc::new(
+                        RoutineEngine::new(
+                            rt_config.clone(),
+                            Arc::clone(store),
+                            self.llm().clone(),
+                            Arc::clone(workspace),
+                            notify_tx,
+                            Some(self.scheduler.clone()),
+                        )
+                        .with_cost_guard(Arc::clone(self.cost_guard()))
+                        .with_matter_root(self.deps.legal_config.matter_root.clone()),
+                    );
 
                     // Register routine tools
                     self.deps
@@ -458,7 +529,13 @@ impl Agent {
                     // Load initial event cache
                     engine.refresh_event_cache().await;
 
-                    // Spawn notification forwarder
+                    if let Some(slot) = self.deps.routine_engine_slot.as_ref() {
+                        slot.set(Arc::clone(&engine));
+                    }
+
+                    // Spawn notification forwarder. Routines with a notify
+                    // channel go only there (e.g. SMS reminders to a client);
+                    // the rest are broadcast on every channel.
                     let channels = self.channels.clone();
                     tokio::spawn(async move {
                         while let Some(response) = notify_rx.recv().await {
@@ -468,6 +545,22 @@ impl Agent {
                                 .and_then(|v| v.as_str())
                                 .unwrap_or("default")
                                 .to_string();
+                            if let Some(channel) = response
+                                .metadata
+                                .get("notify_channel")
+                                .and_then(|v| v.as_str())
+                                .map(String::from)
+                            {
+                                if let Err(e) = channels.broadcast(&channel, &user, response).await
+                                {
+                                    tracing::warn!(
+                                        "Failed to send routine notification to {}: {}",
+                                        channel,
+                                        e
+                                    );
+                                }
+                                continue;
+                            }
                             let results = channels.broadcast_all(&user, response).await;
                             for (ch, result) in results {
                                 if let Err(e) = result {
@@ -730,6 +823,10 @@ impl Agent {
             Submission::Heartbeat => self.process_heartbeat().await,
             Submission::Summarize => self.process_summarize(session, thread_id).await,
             Submission::Suggest => self.process_suggest(session, thread_id).await,
+            Submission::FileToMatter { matter_id, title } => {
+                self.process_file_to_matter(message, session, thread_id, matter_id, title)
+                    .await
+            }
             Submission::Quit => return Ok(None),
             Submission::SwitchThread { thread_id: target } => {
                 self.process_switch_thread(message, target).await
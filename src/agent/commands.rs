@@ -292,7 +292,8 @@ impl Agent {
 
         let request = crate::llm::CompletionRequest::new(context)
             .with_max_tokens(512)
-            .with_temperature(0.3);
+            .with_temperature(0.3)
+            .with_task(crate::llm::LlmTask::Summarization);
 
         let reasoning = Reasoning::new(self.llm().clone(), self.safety().clone());
         match reasoning.complete(request).await {
@@ -340,7 +341,8 @@ impl Agent {
 
         let request = crate::llm::CompletionRequest::new(context)
             .with_max_tokens(512)
-            .with_temperature(0.5);
+            .with_temperature(0.5)
+            .with_cache_control(crate::llm::CacheControl::Bypass);
 
         let reasoning = Reasoning::new(self.llm().clone(), self.safety().clone());
         match reasoning.complete(request).await {
@@ -352,6 +354,103 @@ impl Agent {
         }
     }
 
+    /// File the latest answered exchange of the current thread into a matter.
+    pub(super) async fn process_file_to_matter(
+        &self,
+        message: &IncomingMessage,
+        session: Arc<Mutex<Session>>,
+        thread_id: Uuid,
+        matter_id: Option<String>,
+        title: Option<String>,
+    ) -> Result<SubmissionResult, Error> {
+        let (Some(store), Some(workspace)) = (self.store(), self.workspace()) else {
+            return Ok(SubmissionResult::error(
+                "Filing to a matter requires a database and workspace.",
+            ));
+        };
+        let legal = self.effective_legal_config_for(message);
+        let Some(raw_matter_id) = matter_id.or(legal.active_matter.clone()) else {
+            return Ok(SubmissionResult::error(
+                "Usage: /file <matter_id> [title] (no active matter is set)",
+            ));
+        };
+        let matter_id = crate::legal::policy::sanitize_matter_id(&raw_matter_id);
+        if matter_id.is_empty()
+            || store
+                .get_matter_db(&message.user_id, &matter_id)
+                .await?
+                .is_none()
+        {
+            return Ok(SubmissionResult::error(format!(
+                "Matter '{}' not found.",
+                raw_matter_id
+            )));
+        }
+
+        let exchange = {
+            let sess = session.lock().await;
+            let thread = sess
+                .threads
+                .get(&thread_id)
+                .ok_or_else(|| Error::from(crate::error::JobError::NotFound { id: thread_id }))?;
+            thread.turns.iter().rev().find_map(|turn| {
+                turn.response
+                    .as_ref()
+                    .map(|answer| crate::legal::memo::MemoExchange {
+                        asked_at: Some(turn.started_at),
+                        question: turn.user_input.clone(),
+                        answer: answer.clone(),
+                    })
+            })
+        };
+        let Some(exchange) = exchange else {
+            return Ok(SubmissionResult::ok_with_message(
+                "Nothing to file (no answered exchange in this thread).",
+            ));
+        };
+
+        let exchanges = vec![exchange];
+        let filed_by = message
+            .user_name
+            .clone()
+            .unwrap_or_else(|| message.user_id.clone());
+        let memo = crate::legal::memo::ConversationMemo {
+            matter_id: matter_id.clone(),
+            title: title.unwrap_or_else(|| crate::legal::memo::default_memo_title(&exchanges)),
+            filed_by: filed_by.clone(),
+            filed_at: chrono::Utc::now(),
+            thread_id,
+            model: Some(self.llm().active_model_name()),
+            exchanges,
+        };
+        let document = crate::legal::memo::file_conversation_memo(
+            store.as_ref(),
+            workspace.as_ref(),
+            &message.user_id,
+            &legal.matter_root,
+            &memo,
+        )
+        .await?;
+        crate::legal::audit::record_with_db(
+            "chat_exchange_filed_to_matter",
+            &filed_by,
+            Some(matter_id.as_str()),
+            crate::db::AuditSeverity::Info,
+            serde_json::json!({
+                "thread_id": thread_id.to_string(),
+                "turns": 1,
+                "path": document.path,
+            }),
+            store.as_ref(),
+            &message.user_id,
+        )
+        .await;
+        Ok(SubmissionResult::response(format!(
+            "Filed to matter '{}' as a draft memo: {}",
+            matter_id, document.path
+        )))
+    }
+
     /// Handle system commands that bypass thread-state checks entirely.
     pub(super) async fn handle_system_command(
         &self,
@@ -392,6 +491,7 @@ impl Agent {
                 "  /heartbeat        Run heartbeat check\n",
                 "  /summarize        Summarize current thread\n",
                 "  /suggest          Suggest next steps\n",
+                "  /file [matter]    File last exchange into a matter memo\n",
                 "\n",
                 "  /quit             Exit",
             ))),
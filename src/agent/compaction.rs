@@ -270,7 +270,8 @@ Be brief but capture all important details. Use bullet points."#,
 
         let request = CompletionRequest::new(request_messages)
             .with_max_tokens(1024)
-            .with_temperature(0.3);
+            .with_temperature(0.3)
+            .with_task(crate::llm::LlmTask::Summarization);
 
         let reasoning = Reasoning::new(self.llm.clone(), self.safety.clone());
         let (text, _) = reasoning.complete(request).await?;
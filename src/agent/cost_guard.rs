@@ -3,17 +3,30 @@
 //! Tracks LLM spending and action rates, enforcing configurable limits
 //! to prevent runaway agents from burning through API credits. Especially
 //! important for daemon/heartbeat modes where the agent acts autonomously.
+//!
+//! When a ledger is attached, every LLM call, tool execution, and sandbox job
+//! is also written to the cost ledger against the active matter (and its
+//! client), which backs per-matter reporting and monthly spend caps.
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Instant;
 
+use chrono::{DateTime, Datelike, TimeZone, Utc};
 use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal_macros::dec;
+use serde::Deserialize;
 use tokio::sync::Mutex;
+use uuid::Uuid;
 
+use crate::db::{CostEntryRecord, CostSource, Database, RecordCostEntryParams};
 use crate::llm::costs;
 
+/// Settings key holding a user's active matter.
+const ACTIVE_MATTER_SETTING_KEY: &str = "legal.active_matter";
+
 /// Configuration for cost guardrails.
 #[derive(Debug, Clone, Default)]
 pub struct CostGuardConfig {
@@ -30,6 +43,12 @@ pub enum CostLimitExceeded {
     DailyBudget { spent_cents: u64, limit_cents: u64 },
     /// Hourly action rate limit reached.
     HourlyRate { actions: u64, limit: u64 },
+    /// A matter's monthly spend cap reached.
+    MatterBudget {
+        matter_id: String,
+        spent_cents: u64,
+        limit_cents: u64,
+    },
 }
 
 impl std::fmt::Display for CostLimitExceeded {
@@ -49,6 +68,17 @@ impl std::fmt::Display for CostLimitExceeded {
                 "Hourly action limit exceeded: {} actions of {} allowed per hour",
                 actions, limit
             ),
+            Self::MatterBudget {
+                matter_id,
+                spent_cents,
+                limit_cents,
+            } => write!(
+                f,
+                "Monthly spend cap for matter '{}' exceeded: spent ${:.2} of ${:.2} allowed",
+                matter_id,
+                *spent_cents as f64 / 100.0,
+                *limit_cents as f64 / 100.0
+            ),
         }
     }
 }
@@ -61,6 +91,42 @@ pub struct ModelTokens {
     pub cost: Decimal,
 }
 
+/// Who a cost is billed to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostAttribution {
+    pub user_id: String,
+    /// Active matter at the time of the call, if any.
+    pub matter_id: Option<String>,
+    pub job_id: Option<Uuid>,
+    /// Ledger source for LLM calls; tool executions are always recorded as
+    /// [`CostSource::ToolExecution`].
+    pub source: CostSource,
+}
+
+impl CostAttribution {
+    pub fn new(user_id: impl Into<String>, matter_id: Option<String>) -> Self {
+        Self {
+            user_id: user_id.into(),
+            matter_id,
+            job_id: None,
+            source: CostSource::LlmCall,
+        }
+    }
+
+    /// Attribute to a background job.
+    pub fn with_job(mut self, job_id: Uuid) -> Self {
+        self.job_id = Some(job_id);
+        self
+    }
+
+    /// Attribute to a sandbox (container) job.
+    pub fn sandbox_job(mut self, job_id: Uuid) -> Self {
+        self.job_id = Some(job_id);
+        self.source = CostSource::SandboxJob;
+        self
+    }
+}
+
 /// Tracks costs and action rates, enforcing configurable limits.
 ///
 /// Thread-safe; designed to be shared via `Arc<CostGuard>`.
@@ -78,6 +144,12 @@ pub struct CostGuard {
 
     /// Per-model token usage since startup.
     model_tokens: Mutex<HashMap<String, ModelTokens>>,
+
+    /// Cost ledger for attributed spend. None = in-memory tracking only.
+    ledger: Option<Arc<dyn Database>>,
+
+    /// Client of each (user, matter) seen so far.
+    matter_clients: Mutex<HashMap<(String, String), Uuid>>,
 }
 
 struct DailyCost {
@@ -97,9 +169,17 @@ impl CostGuard {
             action_window: Mutex::new(VecDeque::new()),
             budget_exceeded: AtomicBool::new(false),
             model_tokens: Mutex::new(HashMap::new()),
+            ledger: None,
+            matter_clients: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Persist attributed costs to `ledger` and enforce matter spend caps.
+    pub fn with_ledger(mut self, ledger: Arc<dyn Database>) -> Self {
+        self.ledger = Some(ledger);
+        self
+    }
+
     /// Check whether the next action is allowed under the configured limits.
     ///
     /// Call this BEFORE making an LLM call. Does NOT record the action yet,
@@ -155,53 +235,22 @@ impl CostGuard {
     /// When `cost_per_token` is `Some`, those rates are used directly (provider-
     /// sourced pricing). When `None`, falls back to the static `costs::model_cost`
     /// lookup table, then `costs::default_cost`.
+    ///
+    /// With an `attribution`, the call is also written to the cost ledger.
     pub async fn record_llm_call(
         &self,
         model: &str,
         input_tokens: u32,
         output_tokens: u32,
         cost_per_token: Option<(Decimal, Decimal)>,
+        attribution: Option<&CostAttribution>,
     ) -> Decimal {
         let (input_rate, output_rate) = cost_per_token
             .unwrap_or_else(|| costs::model_cost(model).unwrap_or_else(costs::default_cost));
         let cost =
             input_rate * Decimal::from(input_tokens) + output_rate * Decimal::from(output_tokens);
 
-        // Update daily cost (reset if new day)
-        {
-            let mut daily = self.daily_cost.lock().await;
-            let today = chrono::Utc::now().date_naive();
-            if today != daily.reset_date {
-                daily.total = Decimal::ZERO;
-                daily.reset_date = today;
-                self.budget_exceeded.store(false, Ordering::Relaxed);
-                tracing::info!("Cost guard: daily counter reset for {}", today);
-            }
-            daily.total += cost;
-
-            // Check if we just crossed the threshold
-            if let Some(limit_cents) = self.config.max_cost_per_day_cents {
-                let spent_cents = to_cents(daily.total);
-                if spent_cents >= limit_cents {
-                    self.budget_exceeded.store(true, Ordering::Relaxed);
-                    tracing::warn!(
-                        "Daily cost limit reached: ${:.2} of ${:.2}",
-                        daily.total,
-                        Decimal::from(limit_cents) / dec!(100)
-                    );
-                }
-                // Warn at 80% threshold
-                let warn_threshold = limit_cents * 80 / 100;
-                if spent_cents >= warn_threshold && spent_cents < limit_cents {
-                    tracing::warn!(
-                        "Approaching daily cost limit: ${:.2} of ${:.2} ({}%)",
-                        daily.total,
-                        Decimal::from(limit_cents) / dec!(100),
-                        spent_cents * 100 / limit_cents
-                    );
-                }
-            }
-        }
+        self.add_daily_cost(cost).await;
 
         // Record action in sliding window
         {
@@ -217,10 +266,190 @@ impl CostGuard {
             entry.output_tokens += u64::from(output_tokens);
             entry.cost += cost;
         }
+        crate::observability::metrics::record_llm_call(
+            model,
+            input_tokens,
+            output_tokens,
+            cost.to_f64().unwrap_or(0.0),
+        );
+
+        if let Some(attribution) = attribution {
+            self.persist(
+                attribution,
+                attribution.source,
+                model,
+                input_tokens,
+                output_tokens,
+                cost,
+            )
+            .await;
+        }
 
         cost
     }
 
+    /// Record the reported cost of a tool execution (`ToolOutput::cost`).
+    ///
+    /// Counts toward the daily budget but not the hourly action rate.
+    pub async fn record_tool_cost(
+        &self,
+        tool_name: &str,
+        cost: Decimal,
+        attribution: Option<&CostAttribution>,
+    ) {
+        self.add_daily_cost(cost).await;
+        if let Some(attribution) = attribution {
+            self.persist(
+                attribution,
+                CostSource::ToolExecution,
+                tool_name,
+                0,
+                0,
+                cost,
+            )
+            .await;
+        }
+    }
+
+    /// Check a matter's month-to-date spend against its cap.
+    ///
+    /// Passes when no ledger is attached, the matter has no cap, or the
+    /// ledger cannot be read.
+    pub async fn check_matter_budget(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<(), CostLimitExceeded> {
+        let Some(ledger) = self.ledger.as_ref() else {
+            return Ok(());
+        };
+        let cap = match ledger.get_matter_spend_cap(user_id, matter_id).await {
+            Ok(Some(cap)) => cap,
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                tracing::warn!(matter = %matter_id, "Failed to load matter spend cap: {}", e);
+                return Ok(());
+            }
+        };
+        let spent = match ledger
+            .matter_cost_since(user_id, matter_id, month_start(Utc::now()))
+            .await
+        {
+            Ok(spent) => spent,
+            Err(e) => {
+                tracing::warn!(matter = %matter_id, "Failed to load matter spend: {}", e);
+                return Ok(());
+            }
+        };
+        let spent_cents = to_cents(spent);
+        let limit_cents = cap.monthly_cap_cents.max(0) as u64;
+        if spent_cents >= limit_cents {
+            return Err(CostLimitExceeded::MatterBudget {
+                matter_id: matter_id.to_string(),
+                spent_cents,
+                limit_cents,
+            });
+        }
+        Ok(())
+    }
+
+    async fn persist(
+        &self,
+        attribution: &CostAttribution,
+        source: CostSource,
+        label: &str,
+        input_tokens: u32,
+        output_tokens: u32,
+        cost: Decimal,
+    ) {
+        let Some(ledger) = self.ledger.as_ref() else {
+            return;
+        };
+        let client_id = match attribution.matter_id.as_deref() {
+            Some(matter_id) => {
+                self.matter_client(ledger.as_ref(), &attribution.user_id, matter_id)
+                    .await
+            }
+            None => None,
+        };
+        let params = RecordCostEntryParams {
+            user_id: attribution.user_id.clone(),
+            matter_id: attribution.matter_id.clone(),
+            client_id,
+            source,
+            job_id: attribution.job_id,
+            label: label.to_string(),
+            input_tokens,
+            output_tokens,
+            cost,
+        };
+        if let Err(e) = ledger.record_cost_entry(&params).await {
+            tracing::warn!(
+                source = source.as_str(),
+                label,
+                "Failed to record cost entry: {}",
+                e
+            );
+        }
+    }
+
+    async fn matter_client(
+        &self,
+        ledger: &dyn Database,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Option<Uuid> {
+        let key = (user_id.to_string(), matter_id.to_string());
+        if let Some(client_id) = self.matter_clients.lock().await.get(&key) {
+            return Some(*client_id);
+        }
+        let client_id = match ledger.get_matter_db(user_id, matter_id).await {
+            Ok(matter) => matter?.client_id,
+            Err(e) => {
+                tracing::warn!(matter = %matter_id, "Failed to resolve matter client: {}", e);
+                return None;
+            }
+        };
+        self.matter_clients.lock().await.insert(key, client_id);
+        Some(client_id)
+    }
+
+    /// Add to the daily total, resetting at midnight UTC.
+    async fn add_daily_cost(&self, cost: Decimal) {
+        let mut daily = self.daily_cost.lock().await;
+        let today = chrono::Utc::now().date_naive();
+        if today != daily.reset_date {
+            daily.total = Decimal::ZERO;
+            daily.reset_date = today;
+            self.budget_exceeded.store(false, Ordering::Relaxed);
+            tracing::info!("Cost guard: daily counter reset for {}", today);
+        }
+        daily.total += cost;
+
+        // Check if we just crossed the threshold
+        if let Some(limit_cents) = self.config.max_cost_per_day_cents {
+            let spent_cents = to_cents(daily.total);
+            if spent_cents >= limit_cents {
+                self.budget_exceeded.store(true, Ordering::Relaxed);
+                tracing::warn!(
+                    "Daily cost limit reached: ${:.2} of ${:.2}",
+                    daily.total,
+                    Decimal::from(limit_cents) / dec!(100)
+                );
+            }
+            // Warn at 80% threshold
+            let warn_threshold = limit_cents * 80 / 100;
+            if spent_cents >= warn_threshold && spent_cents < limit_cents {
+                tracing::warn!(
+                    "Approaching daily cost limit: ${:.2} of ${:.2} ({}%)",
+                    daily.total,
+                    Decimal::from(limit_cents) / dec!(100),
+                    spent_cents * 100 / limit_cents
+                );
+            }
+        }
+    }
+
     /// Current daily spend in USD (as Decimal).
     pub async fn daily_spend(&self) -> Decimal {
         let daily = self.daily_cost.lock().await;
@@ -249,11 +478,81 @@ impl CostGuard {
 }
 
 /// Convert a Decimal USD amount to whole cents (truncated).
-fn to_cents(usd: Decimal) -> u64 {
+pub fn to_cents(usd: Decimal) -> u64 {
     let cents = (usd * dec!(100)).trunc();
     cents.to_string().parse::<u64>().unwrap_or(0)
 }
 
+/// Midnight UTC on the first day of `now`'s month; spend caps reset here.
+pub fn month_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now)
+}
+
+/// The user's active matter setting, sanitized.
+pub async fn active_matter_for_user(store: &dyn Database, user_id: &str) -> Option<String> {
+    let setting = store
+        .get_setting(user_id, ACTIVE_MATTER_SETTING_KEY)
+        .await
+        .ok()??;
+    crate::legal::policy::sanitize_optional_matter_id(setting.as_str()?)
+}
+
+/// Dimension for cost reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CostGroupBy {
+    #[default]
+    Matter,
+    Client,
+    Source,
+    Day,
+}
+
+/// Totals for one group of ledger entries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostGroup {
+    /// Matter ID, client ID, source, or `YYYY-MM-DD`; None for unattributed
+    /// spend.
+    pub key: Option<String>,
+    pub cost: Decimal,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub entries: usize,
+}
+
+/// Total ledger entries by `group_by`, most expensive first.
+pub fn group_cost_entries(entries: &[CostEntryRecord], group_by: CostGroupBy) -> Vec<CostGroup> {
+    let mut groups: BTreeMap<Option<String>, CostGroup> = BTreeMap::new();
+    for entry in entries {
+        let key = match group_by {
+            CostGroupBy::Matter => entry.matter_id.clone(),
+            CostGroupBy::Client => entry.client_id.map(|id| id.to_string()),
+            CostGroupBy::Source => Some(entry.source.as_str().to_string()),
+            CostGroupBy::Day => Some(entry.created_at.format("%Y-%m-%d").to_string()),
+        };
+        let group = groups.entry(key.clone()).or_insert_with(|| CostGroup {
+            key,
+            cost: Decimal::ZERO,
+            input_tokens: 0,
+            output_tokens: 0,
+            entries: 0,
+        });
+        group.cost += entry.cost;
+        group.input_tokens += entry.input_tokens;
+        group.output_tokens += entry.output_tokens;
+        group.entries += 1;
+    }
+    let mut groups: Vec<CostGroup> = groups.into_values().collect();
+    if group_by == CostGroupBy::Day {
+        // Chronological reads better for a daily series.
+        return groups;
+    }
+    groups.sort_by_key(|group| std::cmp::Reverse(group.cost));
+    groups
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -267,7 +566,7 @@ mod tests {
 
         // Record a big call, still allowed
         guard
-            .record_llm_call("gpt-4o", 100_000, 100_000, None)
+            .record_llm_call("gpt-4o", 100_000, 100_000, None, None)
             .await;
         assert!(guard.check_allowed().await.is_ok());
     }
@@ -285,7 +584,9 @@ mod tests {
         // Record a call that costs more than $0.01
         // gpt-4o: input=$0.0000025/tok, output=$0.00001/tok
         // 10000 input + 10000 output = $0.025 + $0.10 = $0.125
-        guard.record_llm_call("gpt-4o", 10_000, 10_000, None).await;
+        guard
+            .record_llm_call("gpt-4o", 10_000, 10_000, None, None)
+            .await;
 
         // Now should be blocked
         let result = guard.check_allowed().await;
@@ -308,7 +609,7 @@ mod tests {
         // First 3 actions allowed
         for _ in 0..3 {
             assert!(guard.check_allowed().await.is_ok());
-            guard.record_llm_call("gpt-4o", 10, 10, None).await;
+            guard.record_llm_call("gpt-4o", 10, 10, None, None).await;
         }
 
         // 4th should be blocked
@@ -329,7 +630,7 @@ mod tests {
 
         assert_eq!(guard.daily_spend().await, Decimal::ZERO);
 
-        let cost = guard.record_llm_call("gpt-4o", 1000, 500, None).await;
+        let cost = guard.record_llm_call("gpt-4o", 1000, 500, None, None).await;
         assert!(cost > Decimal::ZERO);
         assert_eq!(guard.daily_spend().await, cost);
     }
@@ -340,8 +641,8 @@ mod tests {
 
         assert_eq!(guard.actions_this_hour().await, 0);
 
-        guard.record_llm_call("gpt-4o", 10, 10, None).await;
-        guard.record_llm_call("gpt-4o", 10, 10, None).await;
+        guard.record_llm_call("gpt-4o", 10, 10, None, None).await;
+        guard.record_llm_call("gpt-4o", 10, 10, None, None).await;
 
         assert_eq!(guard.actions_this_hour().await, 2);
     }
@@ -368,6 +669,14 @@ mod tests {
         };
         assert!(rate.to_string().contains("101 actions"));
         assert!(rate.to_string().contains("100 allowed"));
+
+        let matter = CostLimitExceeded::MatterBudget {
+            matter_id: "acme".to_string(),
+            spent_cents: 2_500,
+            limit_cents: 2_000,
+        };
+        assert!(matter.to_string().contains("matter 'acme'"));
+        assert!(matter.to_string().contains("$25.00 of $20.00"));
     }
 
     #[tokio::test]
@@ -378,10 +687,12 @@ mod tests {
         assert!(guard.model_usage().await.is_empty());
 
         // Record calls for two different models
-        guard.record_llm_call("gpt-4o", 1000, 500, None).await;
-        guard.record_llm_call("gpt-4o", 2000, 1000, None).await;
+        guard.record_llm_call("gpt-4o", 1000, 500, None, None).await;
+        guard
+            .record_llm_call("gpt-4o", 2000, 1000, None, None)
+            .await;
         guard
-            .record_llm_call("claude-3-5-sonnet-20241022", 500, 200, None)
+            .record_llm_call("claude-3-5-sonnet-20241022", 500, 200, None, None)
             .await;
 
         let usage = guard.model_usage().await;
@@ -402,4 +713,142 @@ mod tests {
         // Costs should differ since models have different pricing
         assert_ne!(gpt.cost, claude.cost);
     }
+
+    #[test]
+    fn test_month_start() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 14, 15, 30, 0).unwrap();
+        assert_eq!(
+            month_start(now),
+            Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_group_cost_entries() {
+        let entry = |matter: Option<&str>, source, cost, day| CostEntryRecord {
+            id: Uuid::new_v4(),
+            user_id: "default".to_string(),
+            matter_id: matter.map(str::to_string),
+            client_id: None,
+            source,
+            job_id: None,
+            label: "gpt-4o".to_string(),
+            input_tokens: 10,
+            output_tokens: 5,
+            cost,
+            created_at: Utc.with_ymd_and_hms(2026, 10, day, 12, 0, 0).unwrap(),
+        };
+        let entries = vec![
+            entry(Some("acme"), CostSource::LlmCall, dec!(0.10), 2),
+            entry(Some("globex"), CostSource::ToolExecution, dec!(0.50), 1),
+            entry(Some("acme"), CostSource::SandboxJob, dec!(0.05), 1),
+            entry(None, CostSource::LlmCall, dec!(0.01), 2),
+        ];
+
+        let by_matter = group_cost_entries(&entries, CostGroupBy::Matter);
+        assert_eq!(by_matter.len(), 3);
+        assert_eq!(by_matter[0].key.as_deref(), Some("globex"));
+        assert_eq!(by_matter[1].key.as_deref(), Some("acme"));
+        assert_eq!(by_matter[1].cost, dec!(0.15));
+        assert_eq!(by_matter[1].entries, 2);
+        assert_eq!(by_matter[1].input_tokens, 20);
+        assert_eq!(by_matter[2].key, None);
+
+        let by_day = group_cost_entries(&entries, CostGroupBy::Day);
+        assert_eq!(by_day[0].key.as_deref(), Some("2026-10-01"));
+        assert_eq!(by_day[1].cost, dec!(0.11));
+
+        let by_source = group_cost_entries(&entries, CostGroupBy::Source);
+        assert_eq!(by_source[0].key.as_deref(), Some("tool_execution"));
+        assert_eq!(
+            group_cost_entries(&entries, CostGroupBy::Client)[0].entries,
+            4
+        );
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn test_attributed_costs_are_ledgered_and_capped() {
+        use crate::db::{ClientType, CreateClientParams, MatterStatus, UpsertMatterParams};
+
+        let (db, _tmp) = crate::testing::test_db().await;
+        let client = db
+            .create_client(
+                "default",
+                &CreateClientParams {
+                    name: "Acme Corp".to_string(),
+                    client_type: ClientType::Entity,
+                    email: None,
+                    phone: None,
+                    address: None,
+                    notes: None,
+                },
+            )
+            .await
+            .unwrap();
+        db.upsert_matter(
+            "default",
+            &UpsertMatterParams {
+                matter_id: "acme-v-foo".to_string(),
+                client_id: client.id,
+                status: MatterStatus::Active,
+                stage: None,
+                practice_area: None,
+                jurisdiction: None,
+                opened_at: None,
+                closed_at: None,
+                assigned_to: Vec::new(),
+                custom_fields: serde_json::json!({}),
+            },
+        )
+        .await
+        .unwrap();
+        let guard = CostGuard::new(CostGuardConfig::default()).with_ledger(Arc::clone(&db));
+        let attribution = CostAttribution::new("default", Some("acme-v-foo".to_string()));
+
+        assert!(
+            guard
+                .check_matter_budget("default", "acme-v-foo")
+                .await
+                .is_ok()
+        );
+        guard
+            .record_llm_call("gpt-4o", 10_000, 10_000, None, Some(&attribution))
+            .await;
+        guard
+            .record_tool_cost("http", dec!(0.02), Some(&attribution))
+            .await;
+        guard.record_llm_call("gpt-4o", 10, 10, None, None).await;
+
+        let entries = db.list_cost_entries("default", None, None).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|e| e.client_id == Some(client.id)));
+        assert_eq!(entries[1].source, CostSource::ToolExecution);
+        assert_eq!(entries[1].label, "http");
+
+        // $0.125 + $0.02 spent; a 10 cent cap is exhausted, $1 is not.
+        db.set_matter_spend_cap("default", "acme-v-foo", Some(100))
+            .await
+            .unwrap();
+        assert!(
+            guard
+                .check_matter_budget("default", "acme-v-foo")
+                .await
+                .is_ok()
+        );
+        db.set_matter_spend_cap("default", "acme-v-foo", Some(10))
+            .await
+            .unwrap();
+        match guard.check_matter_budget("default", "acme-v-foo").await {
+            Err(CostLimitExceeded::MatterBudget {
+                spent_cents,
+                limit_cents,
+                ..
+            }) => {
+                assert_eq!(spent_cents, 14);
+                assert_eq!(limit_cents, 10);
+            }
+            other => panic!("Expected MatterBudget, got {:?}", other),
+        }
+    }
 }
@@ -10,6 +10,7 @@ use tokio::task::JoinSet;
 use uuid::Uuid;
 
 use crate::agent::Agent;
+use crate::agent::cost_guard::{CostAttribution, CostGuard};
 use crate::agent::session::{PendingApproval, Session, ThreadState};
 use crate::channels::{IncomingMessage, StatusUpdate};
 use crate::context::JobContext;
@@ -42,9 +43,24 @@ fn is_group_chat_from_trusted_metadata(message: &IncomingMessage) -> bool {
         .is_some_and(|t| t == "group" || t == "channel" || t == "supergroup")
 }
 
-#[derive(Debug, Clone)]
+/// Preferred language of the client on the other end, from channels that
+/// look the client up themselves.
+fn client_language_from_trusted_metadata(message: &IncomingMessage) -> Option<&str> {
+    if message.channel != "sms" {
+        return None;
+    }
+    message
+        .metadata
+        .get("sms_client_language")
+        .and_then(|v| v.as_str())
+}
+
+#[derive(Clone)]
 pub(super) struct ToolAuditContext {
     pub thread_id: String,
+    /// Where reported tool costs are recorded.
+    pub cost_guard: Arc<CostGuard>,
+    pub attribution: CostAttribution,
 }
 
 fn approval_requirement_label(requirement: ApprovalRequirement) -> &'static str {
@@ -133,12 +149,40 @@ impl Agent {
             crate::legal::skeptical::default_enabled_for_legal(&effective_legal_config),
         )
         .await;
+        let thread_config = match self.store() {
+            Some(store) => {
+                crate::agent::thread_config::load_thread_config(store.as_ref(), thread_id).await
+            }
+            None => None,
+        };
         let system_prompt = {
             let base_prompt = system_prompt.unwrap_or_default();
-            let augmented_prompt = crate::legal::skeptical::append_prompt_addendum(
+            let mut augmented_prompt = crate::legal::skeptical::append_prompt_addendum(
                 base_prompt,
                 skeptical_mode_enabled,
             );
+            if let Some(addendum) =
+                client_language_from_trusted_metadata(message).and_then(|language| {
+                    crate::legal::locale::client_reply_addendum(
+                        language,
+                        chrono::Utc::now().date_naive(),
+                    )
+                })
+            {
+                if !augmented_prompt.is_empty() {
+                    augmented_prompt.push_str("\n\n");
+                }
+                augmented_prompt.push_str(&addendum);
+            }
+            if let Some(addendum) = thread_config
+                .as_ref()
+                .and_then(|config| config.prompt_addendum())
+            {
+                if !augmented_prompt.is_empty() {
+                    augmented_prompt.push_str("\n\n");
+                }
+                augmented_prompt.push_str(&addendum);
+            }
             if augmented_prompt.is_empty() {
                 None
             } else {
@@ -248,7 +292,7 @@ impl Agent {
         let mut context_messages = initial_messages;
 
         // Create a JobContext for tool execution (chat doesn't have a real job)
-        let job_ctx = JobContext::with_user(&message.user_id, "chat", "Interactive chat session");
+        let job_ctx = Self::chat_job_context(message, &effective_legal_config);
 
         let max_tool_iterations = self.config.max_tool_iterations;
         // Force a text-only response on the last iteration to guarantee termination
@@ -324,6 +368,12 @@ impl Agent {
                 tool_defs
             };
 
+            // Restrict to the thread's enabled toolset, if it has one.
+            let tool_defs = match thread_config.as_ref() {
+                Some(config) => config.filter_tools(tool_defs),
+                None => tool_defs,
+            };
+
             // Call LLM with current context; force_text drops tools to guarantee a
             // text response on the final iteration.
             let mut context = ReasoningContext::new()
@@ -431,6 +481,10 @@ impl Agent {
                     output.usage.input_tokens,
                     output.usage.output_tokens,
                     Some(self.llm().cost_per_token()),
+                    Some(&Self::chat_cost_attribution(
+                        message,
+                        &effective_legal_config,
+                    )),
                 )
                 .await;
             tracing::debug!(
@@ -521,6 +575,16 @@ impl Agent {
                     for (idx, original_tc) in tool_calls.iter().enumerate() {
                         let mut tc = original_tc.clone();
 
+                        if thread_config
+                            .as_ref()
+                            .is_some_and(|config| !config.allows_tool(&tc.name))
+                        {
+                            let reason =
+                                format!("Tool '{}' is not enabled for this thread", tc.name);
+                            preflight.push((tc, PreflightOutcome::Rejected(reason)));
+                            continue;
+                        }
+
                         // Hook: BeforeToolCall (runs before approval so hooks can
                         // modify parameters — approval is checked on final params)
                         let event = crate::hooks::HookEvent::ToolCall {
@@ -615,9 +679,8 @@ impl Agent {
                     // index so Phase 3 can iterate in original order.
                     let mut exec_results: Vec<Option<Result<String, Error>>> =
                         (0..preflight.len()).map(|_| None).collect();
-                    let tool_audit_ctx = ToolAuditContext {
-                        thread_id: thread_id.to_string(),
-                    };
+                    let tool_audit_ctx =
+                        self.tool_audit_context(thread_id, message, &effective_legal_config);
 
                     if runnable.len() <= 1 {
                         // Single tool (or none): execute inline
@@ -895,6 +958,19 @@ impl Agent {
         }
     }
 
+    pub(super) fn tool_audit_context(
+        &self,
+        thread_id: Uuid,
+        message: &IncomingMessage,
+        legal: &crate::config::LegalConfig,
+    ) -> ToolAuditContext {
+        ToolAuditContext {
+            thread_id: thread_id.to_string(),
+            cost_guard: Arc::clone(self.cost_guard()),
+            attribution: Self::chat_cost_attribution(message, legal),
+        }
+    }
+
     /// Execute a tool for chat (without full job context).
     pub(super) async fn execute_chat_tool(
         &self,
@@ -1033,6 +1109,12 @@ pub(super) async fn execute_chat_tool_standalone(
             reason: e.to_string(),
         })?;
 
+    if let (Some(cost), Some(ctx)) = (result.cost, audit_ctx.as_ref()) {
+        ctx.cost_guard
+            .record_tool_cost(tool_name, cost, Some(&ctx.attribution))
+            .await;
+    }
+
     serde_json::to_string_pretty(&result.result).map_err(|e| {
         crate::error::ToolError::ExecutionFailed {
             name: tool_name.to_string(),
@@ -1408,6 +1490,7 @@ mod tests {
             legal_config,
             hooks,
             cost_guard: Arc::new(CostGuard::new(CostGuardConfig::default())),
+            routine_engine_slot: None,
         };
 
         Agent::new(
@@ -1719,6 +1802,24 @@ mod tests {
         assert!(!super::is_group_chat_from_trusted_metadata(&untrusted));
     }
 
+    #[test]
+    fn test_client_language_metadata_requires_sms_channel() {
+        let metadata = serde_json::json!({ "sms_client_language": "es" });
+        let sms = crate::channels::IncomingMessage::new("sms", "+15551234567", "hola")
+            .with_metadata(metadata.clone());
+        assert_eq!(
+            super::client_language_from_trusted_metadata(&sms),
+            Some("es")
+        );
+
+        let untrusted = crate::channels::IncomingMessage::new("custom-wasm", "user-1", "hola")
+            .with_metadata(metadata);
+        assert_eq!(
+            super::client_language_from_trusted_metadata(&untrusted),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn conversation_matter_mismatch_blocks_before_llm_call() {
         let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
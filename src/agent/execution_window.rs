@@ -0,0 +1,328 @@
+//! Execution windows and blackout dates for scheduled work.
+//!
+//! The firm-level [`ExecutionPolicy`] is stored in the `execution_policy`
+//! setting. It names the firm's timezone, the weekly windows in which
+//! routines and routine-started sandbox jobs may start, and a calendar of
+//! blackout dates on which neither starts. A routine can replace the firm's
+//! routine windows with its own, or keep running through blackouts, via
+//! [`ExecutionOverride`]. Manual fires are never held back.
+//!
+//! Cron routines held outside their window stay due and fire on the first
+//! tick after it opens; held event and webhook fires are dropped.
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::db::Database;
+
+pub const EXECUTION_POLICY_SETTING_KEY: &str = "execution_policy";
+
+/// A weekly time range in the firm's timezone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionWindow {
+    /// Days the window opens on (`["mon", "tue"]`). Empty means every day.
+    #[serde(default)]
+    pub days: Vec<Weekday>,
+    /// Opening time (`"22:00"`).
+    #[serde(with = "hhmm")]
+    pub start: NaiveTime,
+    /// Closing time. At or before `start`, the window runs past midnight
+    /// into the next day.
+    #[serde(with = "hhmm")]
+    pub end: NaiveTime,
+}
+
+impl ExecutionWindow {
+    /// Whether `local` falls inside the window.
+    pub fn contains(&self, local: NaiveDateTime) -> bool {
+        let (date, time) = (local.date(), local.time());
+        let opens_on = |day: NaiveDate| self.days.is_empty() || self.days.contains(&day.weekday());
+        if self.start < self.end {
+            opens_on(date) && self.start <= time && time < self.end
+        } else {
+            (time >= self.start && opens_on(date))
+                || (time < self.end && date.pred_opt().is_some_and(opens_on))
+        }
+    }
+}
+
+/// One or more consecutive days the firm is closed to scheduled work.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlackoutDate {
+    pub date: NaiveDate,
+    /// Last day of a multi-day blackout, inclusive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<NaiveDate>,
+    #[serde(default)]
+    pub label: String,
+}
+
+impl BlackoutDate {
+    fn covers(&self, day: NaiveDate) -> bool {
+        self.date <= day && day <= self.until.unwrap_or(self.date)
+    }
+}
+
+/// Per-routine replacement for the firm's routine windows.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionOverride {
+    /// Windows used instead of the firm's routine windows. An empty list
+    /// lets the routine fire at any time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub windows: Option<Vec<ExecutionWindow>>,
+    /// Keep firing on blackout dates.
+    #[serde(default)]
+    pub ignore_blackouts: bool,
+}
+
+/// Firm-level execution windows and blackout calendar.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionPolicy {
+    /// IANA timezone the windows and dates are read in. UTC when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// When triggered routines may fire. Empty means any time.
+    #[serde(default)]
+    pub routine_windows: Vec<ExecutionWindow>,
+    /// When sandbox jobs started by routines may start. Empty means any time.
+    #[serde(default)]
+    pub background_windows: Vec<ExecutionWindow>,
+    #[serde(default)]
+    pub blackout_dates: Vec<BlackoutDate>,
+}
+
+impl ExecutionPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(tz) = &self.timezone {
+            tz.parse::<Tz>()
+                .map_err(|_| format!("unknown timezone '{}'", tz))?;
+        }
+        if let Some(blackout) = self
+            .blackout_dates
+            .iter()
+            .find(|b| b.until.is_some_and(|until| until < b.date))
+        {
+            return Err(format!(
+                "blackout ending before it starts ({})",
+                blackout.date
+            ));
+        }
+        Ok(())
+    }
+
+    /// `now` as a wall-clock time in the firm's timezone.
+    pub fn local_time(&self, now: DateTime<Utc>) -> NaiveDateTime {
+        let tz = self
+            .timezone
+            .as_deref()
+            .and_then(|tz| tz.parse::<Tz>().ok())
+            .unwrap_or(Tz::UTC);
+        now.with_timezone(&tz).naive_local()
+    }
+
+    pub fn blackout_on(&self, day: NaiveDate) -> Option<&BlackoutDate> {
+        self.blackout_dates.iter().find(|b| b.covers(day))
+    }
+
+    /// Why a routine with `routine_override` may not fire at `now`, if it
+    /// may not.
+    pub fn routine_hold(
+        &self,
+        routine_override: &ExecutionOverride,
+        now: DateTime<Utc>,
+    ) -> Option<String> {
+        let windows = routine_override
+            .windows
+            .as_deref()
+            .unwrap_or(&self.routine_windows);
+        self.hold(windows, !routine_override.ignore_blackouts, now)
+    }
+
+    /// Why a routine-started sandbox job may not start at `now`, if it may not.
+    pub fn background_hold(&self, now: DateTime<Utc>) -> Option<String> {
+        self.hold(&self.background_windows, true, now)
+    }
+
+    fn hold(
+        &self,
+        windows: &[ExecutionWindow],
+        blackouts_apply: bool,
+        now: DateTime<Utc>,
+    ) -> Option<String> {
+        let local = self.local_time(now);
+        if blackouts_apply && let Some(blackout) = self.blackout_on(local.date()) {
+            return Some(if blackout.label.is_empty() {
+                format!("blackout date {}", local.date())
+            } else {
+                format!("blackout date {} ({})", local.date(), blackout.label)
+            });
+        }
+        if !windows.is_empty() && !windows.iter().any(|w| w.contains(local)) {
+            return Some("outside execution window".to_string());
+        }
+        None
+    }
+}
+
+/// Parse and validate a stored `execution_policy` setting value.
+pub fn parse_setting_value(value: &serde_json::Value) -> Result<ExecutionPolicy, String> {
+    let policy: ExecutionPolicy =
+        serde_json::from_value(value.clone()).map_err(|e| e.to_string())?;
+    policy.validate()?;
+    Ok(policy)
+}
+
+/// Load a user's execution policy, falling back to "no restrictions" when
+/// unset or unreadable.
+pub async fn load_policy(store: &dyn Database, user_id: &str) -> ExecutionPolicy {
+    match store
+        .get_setting(user_id, EXECUTION_POLICY_SETTING_KEY)
+        .await
+    {
+        Ok(Some(value)) => parse_setting_value(&value).unwrap_or_else(|e| {
+            tracing::warn!(user_id, "Ignoring invalid execution_policy setting: {}", e);
+            ExecutionPolicy::default()
+        }),
+        Ok(None) => ExecutionPolicy::default(),
+        Err(e) => {
+            tracing::warn!(user_id, "Failed to read execution_policy setting: {}", e);
+            ExecutionPolicy::default()
+        }
+    }
+}
+
+mod hhmm {
+    use chrono::NaiveTime;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(time: &NaiveTime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&time.format("%H:%M").to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveTime, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        let raw = raw.trim();
+        NaiveTime::parse_from_str(raw, "%H:%M")
+            .or_else(|_| NaiveTime::parse_from_str(raw, "%H:%M:%S"))
+            .map_err(|_| D::Error::custom(format!("invalid time '{}' (expected HH:MM)", raw)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn policy(json: serde_json::Value) -> ExecutionPolicy {
+        parse_setting_value(&json).unwrap()
+    }
+
+    #[test]
+    fn overnight_windows_span_midnight() {
+        let policy = policy(serde_json::json!({
+            "routine_windows": [{"days": ["mon"], "start": "22:00", "end": "06:00"}]
+        }));
+        let none = ExecutionOverride::default();
+        // Monday 2026-10-12.
+        assert_eq!(policy.routine_hold(&none, at("2026-10-12T23:30:00Z")), None);
+        assert_eq!(policy.routine_hold(&none, at("2026-10-13T05:59:00Z")), None);
+        assert_eq!(
+            policy
+                .routine_hold(&none, at("2026-10-13T06:00:00Z"))
+                .as_deref(),
+            Some("outside execution window")
+        );
+        // Tuesday night is not a Monday window.
+        assert!(
+            policy
+                .routine_hold(&none, at("2026-10-13T23:00:00Z"))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn windows_and_blackouts_use_the_firm_timezone() {
+        let policy = policy(serde_json::json!({
+            "timezone": "America/New_York",
+            "routine_windows": [{"days": ["Mon", "tue", "wed", "thu", "fri"], "start": "09:00", "end": "17:00"}],
+            "blackout_dates": [{"date": "2026-12-24", "until": "2026-12-26", "label": "Holiday closure"}]
+        }));
+        let none = ExecutionOverride::default();
+        // 13:00 UTC is 09:00 in New York (EDT).
+        assert_eq!(policy.routine_hold(&none, at("2026-10-14T13:00:00Z")), None);
+        assert!(
+            policy
+                .routine_hold(&none, at("2026-10-14T12:59:00Z"))
+                .is_some()
+        );
+        // Saturday is outside the weekday window.
+        assert!(
+            policy
+                .routine_hold(&none, at("2026-10-17T15:00:00Z"))
+                .is_some()
+        );
+        // Friday 2026-12-25 is a weekday, but blacked out.
+        assert_eq!(
+            policy
+                .routine_hold(&none, at("2026-12-25T15:00:00Z"))
+                .as_deref(),
+            Some("blackout date 2026-12-25 (Holiday closure)")
+        );
+        // Background jobs have no windows but still honour blackouts.
+        assert!(policy.background_hold(at("2026-12-26T15:00:00Z")).is_some());
+        assert_eq!(policy.background_hold(at("2026-12-27T15:00:00Z")), None);
+    }
+
+    #[test]
+    fn routine_overrides_replace_firm_windows() {
+        let policy = policy(serde_json::json!({
+            "routine_windows": [{"start": "22:00", "end": "06:00"}],
+            "blackout_dates": [{"date": "2026-10-14"}]
+        }));
+        let anytime = ExecutionOverride {
+            windows: Some(Vec::new()),
+            ignore_blackouts: false,
+        };
+        assert_eq!(
+            policy.routine_hold(&anytime, at("2026-10-15T12:00:00Z")),
+            None
+        );
+        assert!(
+            policy
+                .routine_hold(&anytime, at("2026-10-14T12:00:00Z"))
+                .is_some()
+        );
+
+        let through_blackouts = ExecutionOverride {
+            windows: Some(Vec::new()),
+            ignore_blackouts: true,
+        };
+        assert_eq!(
+            policy.routine_hold(&through_blackouts, at("2026-10-14T12:00:00Z")),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_bad_timezones_and_ranges() {
+        assert!(parse_setting_value(&serde_json::json!({"timezone": "Mars/Olympus"})).is_err());
+        assert!(
+            parse_setting_value(&serde_json::json!({
+                "blackout_dates": [{"date": "2026-12-26", "until": "2026-12-24"}]
+            }))
+            .is_err()
+        );
+        assert!(
+            parse_setting_value(&serde_json::json!({
+                "routine_windows": [{"start": "25:00", "end": "06:00"}]
+            }))
+            .is_err()
+        );
+    }
+}
@@ -16,6 +16,7 @@ pub mod compaction;
 pub mod context_monitor;
 pub mod cost_guard;
 mod dispatcher;
+pub mod execution_window;
 mod heartbeat;
 pub mod job_monitor;
 mod router;
@@ -27,6 +28,7 @@ pub mod session;
 mod session_manager;
 pub mod submission;
 pub mod task;
+pub mod thread_config;
 mod thread_ops;
 pub mod undo;
 pub mod worker;
@@ -38,9 +40,11 @@ pub use context_monitor::{CompactionStrategy, ContextBreakdown, ContextMonitor};
 pub use heartbeat::{HeartbeatConfig, HeartbeatResult, HeartbeatRunner, spawn_heartbeat};
 pub use router::{MessageIntent, Router};
 pub use routine::{Routine, RoutineAction, RoutineRun, Trigger};
-pub use routine_engine::RoutineEngine;
+pub use routine_engine::{RoutineEngine, RoutineEngineSlot};
 pub use scheduler::Scheduler;
-pub use self_repair::{BrokenTool, RepairResult, RepairTask, SelfRepair, StuckJob};
+pub use self_repair::{
+    BROKEN_TOOL_FAILURE_THRESHOLD, BrokenTool, RepairResult, RepairTask, SelfRepair, StuckJob,
+};
 pub use session::{PendingApproval, PendingAuth, Session, Thread, ThreadState, Turn, TurnState};
 pub use session_manager::SessionManager;
 pub use submission::{Submission, SubmissionParser, SubmissionResult};
@@ -18,16 +18,24 @@
 //! ```
 
 use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
 use uuid::Uuid;
 
+use crate::agent::execution_window::ExecutionOverride;
 use crate::error::RoutineError;
 
+/// Event-trigger channel filter that matches domain event names (such as
+/// `matter.created`) from the event bus instead of channel messages.
+pub const DOMAIN_EVENT_CHANNEL: &str = "events";
+
 /// A routine is a named, persistent, user-owned task with a trigger and an action.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Routine {
@@ -40,6 +48,9 @@ pub struct Routine {
     pub action: RoutineAction,
     pub guardrails: RoutineGuardrails,
     pub notify: NotifyConfig,
+    /// Routines fired when this one finishes.
+    #[serde(default)]
+    pub chain: RoutineChain,
 
     // Runtime state (DB-managed)
     pub last_run_at: Option<DateTime<Utc>>,
@@ -56,20 +67,32 @@ pub struct Routine {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Trigger {
-    /// Fire on a cron schedule (e.g. "0 9 * * MON-FRI" or "every 2h").
-    Cron { schedule: String },
+    /// Fire on a 6-field cron schedule (e.g. "0 0 9 * * MON-FRI"). See
+    /// [`parse_schedule`] for the forms accepted from users.
+    Cron {
+        schedule: String,
+        /// IANA timezone the schedule is read in. UTC when unset.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timezone: Option<String>,
+        /// Random delay of up to this many seconds added to each fire, so
+        /// routines sharing a schedule do not all start at once.
+        #[serde(default)]
+        jitter_secs: u64,
+    },
     /// Fire when a channel message matches a pattern.
     Event {
-        /// Optional channel filter (e.g. "telegram", "slack").
+        /// Optional channel filter (e.g. "telegram", "slack", or
+        /// [`DOMAIN_EVENT_CHANNEL`] for domain events).
         channel: Option<String>,
         /// Regex pattern to match against message content.
         pattern: String,
     },
-    /// Fire on incoming webhook POST to /hooks/routine/{id}.
+    /// Fire on incoming webhook POST to /api/routines/{id}/webhook/{token}.
     Webhook {
         /// Optional webhook path suffix (defaults to routine id).
         path: Option<String>,
-        /// Optional shared secret for HMAC validation.
+        /// Token that must appear in the webhook URL. Routines without one
+        /// cannot be fired by webhook.
         secret: Option<String>,
     },
     /// Only fires via tool call or CLI.
@@ -99,7 +122,19 @@ impl Trigger {
                         field: "schedule".into(),
                     })?
                     .to_string();
-                Ok(Trigger::Cron { schedule })
+                let timezone = config
+                    .get("timezone")
+                    .and_then(|v| v.as_str())
+                    .map(String::from);
+                let jitter_secs = config
+                    .get("jitter_secs")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(0);
+                Ok(Trigger::Cron {
+                    schedule,
+                    timezone,
+                    jitter_secs,
+                })
             }
             "event" => {
                 let pattern = config
@@ -137,7 +172,15 @@ impl Trigger {
     /// Serialize trigger-specific config to JSON for DB storage.
     pub fn to_config_json(&self) -> serde_json::Value {
         match self {
-            Trigger::Cron { schedule } => serde_json::json!({ "schedule": schedule }),
+            Trigger::Cron {
+                schedule,
+                timezone,
+                jitter_secs,
+            } => serde_json::json!({
+                "schedule": schedule,
+                "timezone": timezone,
+                "jitter_secs": jitter_secs,
+            }),
             Trigger::Event { channel, pattern } => serde_json::json!({
                 "pattern": pattern,
                 "channel": channel,
@@ -149,6 +192,24 @@ impl Trigger {
             Trigger::Manual => serde_json::json!({}),
         }
     }
+
+    /// True for a webhook trigger whose URL token equals `token`.
+    pub fn accepts_webhook_token(&self, token: &str) -> bool {
+        match self {
+            Trigger::Webhook {
+                secret: Some(secret),
+                ..
+            } if !secret.is_empty() => secret.as_bytes().ct_eq(token.as_bytes()).into(),
+            _ => false,
+        }
+    }
+}
+
+/// Random URL token for a new webhook-triggered routine.
+pub fn generate_webhook_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill(&mut bytes);
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
 }
 
 /// What happens when a routine fires.
@@ -176,6 +237,23 @@ pub enum RoutineAction {
         #[serde(default = "default_max_iterations")]
         max_iterations: u32,
     },
+    /// Compile a client-ready status memo from matter records into the
+    /// matter's `exports/` folder. No LLM call.
+    MatterStatusReport {
+        /// Matter to report on (default: every active matter).
+        #[serde(default)]
+        matter_id: Option<String>,
+        /// Opening paragraph for the memo.
+        #[serde(default)]
+        note: Option<String>,
+        /// Days of activity covered (default: 7).
+        #[serde(default = "default_report_period_days")]
+        period_days: u32,
+        /// Queue the memo for attorney approval, after which it is emailed
+        /// to the client.
+        #[serde(default)]
+        send: bool,
+    },
 }
 
 fn default_max_tokens() -> u32 {
@@ -186,12 +264,17 @@ fn default_max_iterations() -> u32 {
     10
 }
 
+pub(crate) fn default_report_period_days() -> u32 {
+    7
+}
+
 impl RoutineAction {
     /// The string tag stored in the DB action_type column.
     pub fn type_tag(&self) -> &'static str {
         match self {
             RoutineAction::Lightweight { .. } => "lightweight",
             RoutineAction::FullJob { .. } => "full_job",
+            RoutineAction::MatterStatusReport { .. } => "matter_status_report",
         }
     }
 
@@ -254,6 +337,29 @@ impl RoutineAction {
                     max_iterations,
                 })
             }
+            "matter_status_report" => {
+                let text = |field: &str| {
+                    config
+                        .get(field)
+                        .and_then(|v| v.as_str())
+                        .map(str::trim)
+                        .filter(|v| !v.is_empty())
+                        .map(String::from)
+                };
+                Ok(RoutineAction::MatterStatusReport {
+                    matter_id: text("matter_id"),
+                    note: text("note"),
+                    period_days: config
+                        .get("period_days")
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(default_report_period_days() as u64)
+                        as u32,
+                    send: config
+                        .get("send")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false),
+                })
+            }
             other => Err(RoutineError::UnknownActionType {
                 action_type: other.to_string(),
             }),
@@ -281,6 +387,17 @@ impl RoutineAction {
                 "description": description,
                 "max_iterations": max_iterations,
             }),
+            RoutineAction::MatterStatusReport {
+                matter_id,
+                note,
+                period_days,
+                send,
+            } => serde_json::json!({
+                "matter_id": matter_id,
+                "note": note,
+                "period_days": period_days,
+                "send": send,
+            }),
         }
     }
 }
@@ -294,6 +411,19 @@ pub struct RoutineGuardrails {
     pub max_concurrent: u32,
     /// Window for content-hash dedup (event triggers). None = no dedup.
     pub dedup_window: Option<Duration>,
+    /// Keep firing after the matter's monthly spend cap is exhausted.
+    #[serde(default)]
+    pub urgent: bool,
+    /// Replaces the firm's routine execution windows for this routine.
+    #[serde(default)]
+    pub execution: ExecutionOverride,
+}
+
+impl RoutineGuardrails {
+    /// The execution override as stored in the `execution_override` column.
+    pub fn execution_json(&self) -> serde_json::Value {
+        serde_json::to_value(&self.execution).unwrap_or_else(|_| serde_json::json!({}))
+    }
 }
 
 impl Default for RoutineGuardrails {
@@ -302,6 +432,8 @@ impl Default for RoutineGuardrails {
             cooldown: Duration::from_secs(300),
             max_concurrent: 1,
             dedup_window: None,
+            urgent: false,
+            execution: ExecutionOverride::default(),
         }
     }
 }
@@ -333,6 +465,199 @@ impl Default for NotifyConfig {
     }
 }
 
+/// Longest chain of routines one fire may set off. Save-time cycle checks
+/// keep chains finite; this bounds runs started from rows edited by hand.
+pub const MAX_CHAIN_DEPTH: u32 = 8;
+
+/// Follow-up routines fired when a routine finishes.
+///
+/// Targets are other routines of the same owner, referenced by name, and
+/// receive the finished run's status and summary as their payload. For
+/// `full_job` routines the chain fires once the job has been dispatched.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoutineChain {
+    /// Routines fired after every run that finishes `ok` or `attention`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_success: Vec<String>,
+    /// Routines fired when the run's result matches a condition.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_condition: Vec<ChainCondition>,
+}
+
+/// Fire `routine` when a finished run matches both filters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainCondition {
+    /// Name of the routine to fire.
+    pub routine: String,
+    /// Run statuses that match (default: `ok` and `attention`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status: Vec<RunStatus>,
+    /// Case-insensitive regex the run summary must match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+}
+
+impl ChainCondition {
+    fn matches(&self, status: RunStatus, summary: Option<&str>) -> bool {
+        let status_matches = if self.status.is_empty() {
+            matches!(status, RunStatus::Ok | RunStatus::Attention)
+        } else {
+            self.status.contains(&status)
+        };
+        if !status_matches {
+            return false;
+        }
+        let Some(pattern) = self.pattern.as_deref() else {
+            return true;
+        };
+        match regex::RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+        {
+            Ok(re) => summary.is_some_and(|summary| re.is_match(summary)),
+            Err(e) => {
+                tracing::warn!(routine = %self.routine, "Invalid chain pattern '{}': {}", pattern, e);
+                false
+            }
+        }
+    }
+}
+
+impl RoutineChain {
+    pub fn is_empty(&self) -> bool {
+        self.on_success.is_empty() && self.on_condition.is_empty()
+    }
+
+    /// Every routine the chain can fire, without duplicates.
+    pub fn targets(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        self.on_success
+            .iter()
+            .map(String::as_str)
+            .chain(self.on_condition.iter().map(|c| c.routine.as_str()))
+            .filter(|name| seen.insert(*name))
+            .collect()
+    }
+
+    /// Routines to fire after a run finished with `status` and `summary`.
+    pub fn fired_by(&self, status: RunStatus, summary: Option<&str>) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        let on_success = self
+            .on_success
+            .iter()
+            .filter(|_| matches!(status, RunStatus::Ok | RunStatus::Attention))
+            .map(String::as_str);
+        let on_condition = self
+            .on_condition
+            .iter()
+            .filter(|c| c.matches(status, summary))
+            .map(|c| c.routine.as_str());
+        on_success
+            .chain(on_condition)
+            .filter(|name| seen.insert(*name))
+            .collect()
+    }
+
+    /// The chain as stored in the `chain` column.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    /// Check that names are set, patterns compile, and statuses are final.
+    fn validate(&self) -> Result<(), RoutineError> {
+        let invalid = |reason: String| Err(RoutineError::InvalidChain { reason });
+        for name in &self.on_success {
+            if name.trim().is_empty() {
+                return invalid("on_success entries must name a routine".to_string());
+            }
+        }
+        for condition in &self.on_condition {
+            if condition.routine.trim().is_empty() {
+                return invalid("on_condition entries must name a routine".to_string());
+            }
+            if condition.status.contains(&RunStatus::Running) {
+                return invalid(format!(
+                    "condition for '{}' cannot match the running status",
+                    condition.routine
+                ));
+            }
+            if let Some(pattern) = condition.pattern.as_deref()
+                && let Err(e) = regex::Regex::new(pattern)
+            {
+                return invalid(format!(
+                    "invalid pattern for '{}': {}",
+                    condition.routine, e
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Validate `routine.chain` against the owner's saved routines: every target
+/// must exist, and the chain must not lead back to `routine`.
+///
+/// `existing` may include the saved copy of `routine`; it is replaced by
+/// `routine` so renames and edited chains are checked as they will be stored.
+pub fn check_chain(routine: &Routine, existing: &[Routine]) -> Result<(), RoutineError> {
+    routine.chain.validate()?;
+    let mut graph: HashMap<&str, Vec<&str>> = existing
+        .iter()
+        .filter(|r| r.id != routine.id)
+        .map(|r| (r.name.as_str(), r.chain.targets()))
+        .collect();
+    graph.insert(routine.name.as_str(), routine.chain.targets());
+
+    if let Some(missing) = routine
+        .chain
+        .targets()
+        .into_iter()
+        .find(|name| !graph.contains_key(name))
+    {
+        return Err(RoutineError::InvalidChain {
+            reason: format!("no routine named '{missing}'"),
+        });
+    }
+    if let Some(cycle) = find_cycle_from(&graph, &routine.name) {
+        return Err(RoutineError::InvalidChain {
+            reason: format!("chain loops back: {}", cycle.join(" -> ")),
+        });
+    }
+    Ok(())
+}
+
+/// Depth-first search for a path from `start` back to itself.
+fn find_cycle_from(graph: &HashMap<&str, Vec<&str>>, start: &str) -> Option<Vec<String>> {
+    fn visit<'a>(
+        graph: &HashMap<&'a str, Vec<&'a str>>,
+        start: &str,
+        node: &'a str,
+        path: &mut Vec<&'a str>,
+        visited: &mut HashSet<&'a str>,
+    ) -> bool {
+        for &next in graph.get(node).into_iter().flatten() {
+            if next == start {
+                path.push(next);
+                return true;
+            }
+            if visited.insert(next) {
+                path.push(next);
+                if visit(graph, start, next, path, visited) {
+                    return true;
+                }
+                path.pop();
+            }
+        }
+        false
+    }
+
+    let (&start_key, _) = graph.get_key_value(start)?;
+    let mut path = vec![start_key];
+    let mut visited = HashSet::from([start_key]);
+    visit(graph, start, start_key, &mut path, &mut visited)
+        .then(|| path.into_iter().map(String::from).collect())
+}
+
 /// Status of a routine run.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -392,29 +717,352 @@ pub fn content_hash(content: &str) -> u64 {
     hasher.finish()
 }
 
-/// Parse a cron expression and compute the next fire time from now.
+/// Longest jitter a cron trigger may add to its fires.
+pub const MAX_CRON_JITTER_SECS: u64 = 60 * 60;
+
+/// Parse a cron expression and compute the next fire time from now (UTC).
 pub fn next_cron_fire(schedule: &str) -> Result<Option<DateTime<Utc>>, RoutineError> {
+    next_cron_fire_in(schedule, None, 0)
+}
+
+/// Compute the next fire time of a cron schedule read in `timezone`, plus a
+/// random delay of up to `jitter_secs`.
+///
+/// The schedule is evaluated on the local wall clock, so "9am" stays 9am
+/// across DST changes. Local times skipped by a spring-forward change do not
+/// fire that day; times repeated by a fall-back change fire once.
+pub fn next_cron_fire_in(
+    schedule: &str,
+    timezone: Option<&str>,
+    jitter_secs: u64,
+) -> Result<Option<DateTime<Utc>>, RoutineError> {
     let cron_schedule =
         cron::Schedule::from_str(schedule).map_err(|e| RoutineError::InvalidCron {
             reason: e.to_string(),
         })?;
-    Ok(cron_schedule.upcoming(Utc).next())
+    let tz = match timezone {
+        Some(name) => parse_timezone(name)?,
+        None => chrono_tz::Tz::UTC,
+    };
+    let Some(next) = fires_after(&cron_schedule, tz, Utc::now()).next() else {
+        return Ok(None);
+    };
+    let jitter = rand::thread_rng().gen_range(0..=jitter_secs.min(MAX_CRON_JITTER_SECS));
+    Ok(Some(next + chrono::Duration::seconds(jitter as i64)))
+}
+
+/// Fire times of `schedule` on `tz`'s wall clock after `after`, in UTC.
+fn fires_after(
+    schedule: &cron::Schedule,
+    tz: chrono_tz::Tz,
+    after: DateTime<Utc>,
+) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+    schedule
+        .after(&after.with_timezone(&tz))
+        .map(|fire| fire.with_timezone(&Utc))
+}
+
+/// Parse an IANA timezone name (`America/New_York`).
+pub fn parse_timezone(name: &str) -> Result<chrono_tz::Tz, RoutineError> {
+    name.trim().parse().map_err(|_| RoutineError::InvalidCron {
+        reason: format!("unknown timezone '{}'", name.trim()),
+    })
+}
+
+impl Trigger {
+    /// Build a cron trigger from user input. The schedule may be cron or
+    /// plain English (see [`parse_schedule`]); it is stored as cron.
+    pub fn cron_from_input(
+        schedule: &str,
+        timezone: Option<&str>,
+        jitter_secs: u64,
+    ) -> Result<Self, RoutineError> {
+        let schedule = parse_schedule(schedule)?;
+        let timezone = timezone.map(str::trim).filter(|tz| !tz.is_empty());
+        if let Some(tz) = timezone {
+            parse_timezone(tz)?;
+        }
+        if jitter_secs > MAX_CRON_JITTER_SECS {
+            return Err(RoutineError::InvalidCron {
+                reason: format!("jitter_secs must be at most {}", MAX_CRON_JITTER_SECS),
+            });
+        }
+        Ok(Trigger::Cron {
+            schedule,
+            timezone: timezone.map(String::from),
+            jitter_secs,
+        })
+    }
+
+    /// Next fire time for cron triggers; `None` for the others.
+    pub fn next_fire(&self) -> Result<Option<DateTime<Utc>>, RoutineError> {
+        match self {
+            Trigger::Cron {
+                schedule,
+                timezone,
+                jitter_secs,
+            } => next_cron_fire_in(schedule, timezone.as_deref(), *jitter_secs),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Normalize a schedule typed by a user into a 6-field cron expression.
+///
+/// Accepts 6/7-field cron, classic 5-field cron (a seconds field is added),
+/// and plain-English schedules:
+///
+/// - `every 15 minutes`, `every 2h`, `hourly`
+/// - `every day at 9am`, `daily at 17:30`
+/// - `every weekday at 9am`, `weekends at noon`
+/// - `every monday and thursday at 8:30am`
+/// - `monthly on the 1st at 9am`
+pub fn parse_schedule(input: &str) -> Result<String, RoutineError> {
+    let input = input.trim();
+    let fields = input.split_whitespace().count();
+    if fields == 5 {
+        let expanded = format!("0 {}", input);
+        if cron::Schedule::from_str(&expanded).is_ok() {
+            return Ok(expanded);
+        }
+    }
+    if (6..=7).contains(&fields) && cron::Schedule::from_str(input).is_ok() {
+        return Ok(input.to_string());
+    }
+    parse_natural_schedule(input).ok_or_else(|| RoutineError::InvalidCron {
+        reason: format!(
+            "could not understand schedule '{}' (use cron or e.g. 'every weekday at 9am')",
+            input
+        ),
+    })
+}
+
+fn parse_natural_schedule(input: &str) -> Option<String> {
+    if input.is_empty() {
+        return None;
+    }
+    let lower = input.to_ascii_lowercase().replace(',', " ");
+    let (days, time) = match lower.split_once(" at ") {
+        Some((days, time)) => (days.trim(), Some(time.trim())),
+        None => (lower.trim(), None),
+    };
+    let days = days.strip_prefix("every").unwrap_or(days).trim();
+
+    if time.is_none() {
+        match days {
+            "minute" => return Some("0 * * * * *".to_string()),
+            "hour" | "hourly" => return Some("0 0 * * * *".to_string()),
+            _ => {}
+        }
+        if let Some(interval) = parse_interval(days) {
+            return Some(interval);
+        }
+    }
+
+    let (hour, minute) = match time {
+        Some(time) => parse_clock_time(time)?,
+        None => (0, 0),
+    };
+    let (day_of_month, day_of_week) = match days {
+        "" | "day" | "daily" => ("*".to_string(), "*".to_string()),
+        "weekday" | "weekdays" => ("*".to_string(), "MON-FRI".to_string()),
+        "weekend" | "weekends" => ("*".to_string(), "SAT,SUN".to_string()),
+        _ => {
+            if let Some(day) = days
+                .strip_prefix("month on the")
+                .or_else(|| days.strip_prefix("monthly on the"))
+            {
+                (parse_day_of_month(day.trim())?.to_string(), "*".to_string())
+            } else {
+                let names = days
+                    .split_whitespace()
+                    .filter(|word| *word != "and")
+                    .map(parse_weekday_name)
+                    .collect::<Option<Vec<_>>>()?;
+                if names.is_empty() {
+                    return None;
+                }
+                ("*".to_string(), names.join(","))
+            }
+        }
+    };
+    Some(format!(
+        "0 {} {} {} * {}",
+        minute, hour, day_of_month, day_of_week
+    ))
+}
+
+/// `15 minutes`, `2h`, `3 hours` -> cron.
+fn parse_interval(value: &str) -> Option<String> {
+    let digits = value.chars().take_while(|c| c.is_ascii_digit()).count();
+    let count: u32 = value[..digits].parse().ok()?;
+    match value[digits..].trim() {
+        "m" | "min" | "mins" | "minute" | "minutes" if (1..60).contains(&count) => {
+            Some(format!("0 */{} * * * *", count))
+        }
+        "h" | "hr" | "hrs" | "hour" | "hours" if (1..24).contains(&count) => {
+            Some(format!("0 0 */{} * * *", count))
+        }
+        _ => None,
+    }
+}
+
+/// `9am`, `9:30 pm`, `17:30`, `noon`, `midnight` -> (hour, minute).
+fn parse_clock_time(value: &str) -> Option<(u32, u32)> {
+    match value {
+        "noon" => return Some((12, 0)),
+        "midnight" => return Some((0, 0)),
+        _ => {}
+    }
+    let (clock, meridiem) = if let Some(clock) = value.strip_suffix("am") {
+        (clock.trim(), Some(false))
+    } else if let Some(clock) = value.strip_suffix("pm") {
+        (clock.trim(), Some(true))
+    } else {
+        (value, None)
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) => (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?),
+        None => (clock.parse::<u32>().ok()?, 0),
+    };
+    if minute > 59 {
+        return None;
+    }
+    let hour = match meridiem {
+        Some(pm) if (1..=12).contains(&hour) => hour % 12 + if pm { 12 } else { 0 },
+        Some(_) => return None,
+        None if hour < 24 => hour,
+        None => return None,
+    };
+    Some((hour, minute))
+}
+
+/// `1st`, `15th`, `3` -> day of month.
+fn parse_day_of_month(value: &str) -> Option<u32> {
+    let digits: String = value.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let suffix = &value[digits.len()..];
+    if !matches!(suffix, "" | "st" | "nd" | "rd" | "th") {
+        return None;
+    }
+    digits.parse().ok().filter(|day| (1..=31).contains(day))
+}
+
+/// `monday`, `mondays`, `mon` -> `MON`.
+fn parse_weekday_name(value: &str) -> Option<&'static str> {
+    const DAYS: [(&str, &str); 7] = [
+        ("monday", "MON"),
+        ("tuesday", "TUE"),
+        ("wednesday", "WED"),
+        ("thursday", "THU"),
+        ("friday", "FRI"),
+        ("saturday", "SAT"),
+        ("sunday", "SUN"),
+    ];
+    let value = value.strip_suffix('s').unwrap_or(value);
+    DAYS.iter()
+        .find(|(name, short)| *name == value || short.eq_ignore_ascii_case(value))
+        .map(|(_, short)| *short)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::agent::routine::{
-        RoutineAction, RoutineGuardrails, RunStatus, Trigger, content_hash, next_cron_fire,
+        ChainCondition, NotifyConfig, Routine, RoutineAction, RoutineChain, RoutineGuardrails,
+        RunStatus, Trigger, check_chain, content_hash, fires_after, generate_webhook_token,
+        next_cron_fire, next_cron_fire_in, parse_schedule,
     };
 
     #[test]
     fn test_trigger_roundtrip() {
         let trigger = Trigger::Cron {
-            schedule: "0 9 * * MON-FRI".to_string(),
+            schedule: "0 0 9 * * MON-FRI".to_string(),
+            timezone: Some("Europe/London".to_string()),
+            jitter_secs: 120,
         };
         let json = trigger.to_config_json();
         let parsed = Trigger::from_db("cron", json).expect("parse cron");
-        assert!(matches!(parsed, Trigger::Cron { schedule } if schedule == "0 9 * * MON-FRI"));
+        assert!(matches!(
+            parsed,
+            Trigger::Cron { schedule, timezone, jitter_secs: 120 }
+                if schedule == "0 0 9 * * MON-FRI" && timezone.as_deref() == Some("Europe/London")
+        ));
+
+        // Rows written before timezones existed still load.
+        let legacy = Trigger::from_db("cron", serde_json::json!({ "schedule": "0 0 * * * *" }))
+            .expect("parse legacy cron");
+        assert!(matches!(
+            legacy,
+            Trigger::Cron {
+                timezone: None,
+                jitter_secs: 0,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parse_schedule_accepts_cron_and_plain_english() {
+        let cases = [
+            ("0 9 * * MON-FRI", "0 0 9 * * MON-FRI"),
+            ("0 30 8 * * *", "0 30 8 * * *"),
+            ("every weekday at 9am", "0 0 9 * * MON-FRI"),
+            ("Every day at 5:30 PM", "0 30 17 * * *"),
+            ("daily at noon", "0 0 12 * * *"),
+            ("weekends at 10", "0 0 10 * * SAT,SUN"),
+            ("every Monday and Thursday at 8:15am", "0 15 8 * * MON,THU"),
+            ("every mon, wed, fri at 12am", "0 0 0 * * MON,WED,FRI"),
+            ("monthly on the 1st at 9am", "0 0 9 1 * *"),
+            ("every 15 minutes", "0 */15 * * * *"),
+            ("every 2h", "0 0 */2 * * *"),
+            ("hourly", "0 0 * * * *"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse_schedule(input).unwrap(), expected, "{input}");
+            assert!(next_cron_fire(expected).is_ok(), "{expected}");
+        }
+        for bad in [
+            "every blue moon",
+            "every day at 13pm",
+            "every 90 minutes",
+            "",
+        ] {
+            assert!(parse_schedule(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_next_cron_fire_follows_local_time_across_dst() {
+        use chrono::{TimeZone, Timelike};
+
+        let schedule = "0 0 9 * * *";
+        let tz: chrono_tz::Tz = "America/New_York".parse().unwrap();
+        let cron = <cron::Schedule as std::str::FromStr>::from_str(schedule).unwrap();
+        // US clocks spring forward on 2026-03-08: 9am EST is 14:00 UTC,
+        // 9am EDT is 13:00 UTC.
+        let before = chrono::Utc.with_ymd_and_hms(2026, 3, 6, 17, 0, 0).unwrap();
+        let hours: Vec<u32> = fires_after(&cron, tz, before)
+            .take(3)
+            .map(|t| t.hour())
+            .collect();
+        assert_eq!(hours, vec![14, 13, 13]);
+
+        let next = next_cron_fire_in(schedule, Some("America/New_York"), 0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.with_timezone(&tz).hour(), 9);
+        assert!(next_cron_fire_in(schedule, Some("Mars/Olympus"), 0).is_err());
+    }
+
+    #[test]
+    fn test_jitter_delays_fire_within_bound() {
+        let exact = next_cron_fire_in("0 0 0 1 1 *", None, 0).unwrap().unwrap();
+        for _ in 0..20 {
+            let jittered = next_cron_fire_in("0 0 0 1 1 *", None, 300)
+                .unwrap()
+                .unwrap();
+            let delay = (jittered - exact).num_seconds();
+            assert!((0..=300).contains(&delay), "{delay}");
+        }
     }
 
     #[test]
@@ -459,6 +1107,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_action_matter_status_report_roundtrip() {
+        let action = RoutineAction::MatterStatusReport {
+            matter_id: Some("acme".to_string()),
+            note: None,
+            period_days: 14,
+            send: true,
+        };
+        assert_eq!(action.type_tag(), "matter_status_report");
+        let json = action.to_config_json();
+        let parsed =
+            RoutineAction::from_db("matter_status_report", json).expect("parse status report");
+        assert!(
+            matches!(parsed, RoutineAction::MatterStatusReport { matter_id, note, period_days, send }
+            if matter_id.as_deref() == Some("acme") && note.is_none() && period_days == 14 && send)
+        );
+
+        let defaults = RoutineAction::from_db("matter_status_report", serde_json::json!({}))
+            .expect("parse defaults");
+        assert!(matches!(
+            defaults,
+            RoutineAction::MatterStatusReport {
+                matter_id: None,
+                period_days: 7,
+                send: false,
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn test_run_status_display_parse() {
         for status in [
@@ -508,7 +1186,9 @@ mod tests {
     fn test_trigger_type_tag() {
         assert_eq!(
             Trigger::Cron {
-                schedule: String::new()
+                schedule: String::new(),
+                timezone: None,
+                jitter_secs: 0,
             }
             .type_tag(),
             "cron"
@@ -531,4 +1211,145 @@ mod tests {
         );
         assert_eq!(Trigger::Manual.type_tag(), "manual");
     }
+
+    #[test]
+    fn test_webhook_token_must_match() {
+        let token = generate_webhook_token();
+        assert_eq!(token.len(), 64);
+        assert_ne!(token, generate_webhook_token());
+
+        let trigger = Trigger::Webhook {
+            path: None,
+            secret: Some(token.clone()),
+        };
+        assert!(trigger.accepts_webhook_token(&token));
+        assert!(!trigger.accepts_webhook_token(&token[..63]));
+        assert!(!trigger.accepts_webhook_token(""));
+
+        for trigger in [
+            Trigger::Webhook {
+                path: None,
+                secret: None,
+            },
+            Trigger::Webhook {
+                path: None,
+                secret: Some(String::new()),
+            },
+            Trigger::Manual,
+        ] {
+            assert!(!trigger.accepts_webhook_token(""));
+            assert!(!trigger.accepts_webhook_token(&token));
+        }
+    }
+
+    fn chained(name: &str, on_success: &[&str]) -> Routine {
+        Routine {
+            id: uuid::Uuid::new_v4(),
+            name: name.to_string(),
+            description: String::new(),
+            user_id: "default".to_string(),
+            enabled: true,
+            trigger: Trigger::Manual,
+            action: RoutineAction::Lightweight {
+                prompt: "check".to_string(),
+                context_paths: Vec::new(),
+                max_tokens: 4096,
+            },
+            guardrails: RoutineGuardrails::default(),
+            notify: NotifyConfig::default(),
+            chain: RoutineChain {
+                on_success: on_success.iter().map(|s| s.to_string()).collect(),
+                on_condition: Vec::new(),
+            },
+            last_run_at: None,
+            next_fire_at: None,
+            run_count: 0,
+            consecutive_failures: 0,
+            state: serde_json::json!({}),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_chain_fires_on_status_and_summary_pattern() {
+        let chain: RoutineChain = serde_json::from_value(serde_json::json!({
+            "on_success": ["digest"],
+            "on_condition": [
+                {"routine": "compute-deadlines", "status": ["attention"], "pattern": "new order"},
+                {"routine": "page-attorney", "status": ["failed"]},
+                {"routine": "digest"}
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            chain.fired_by(RunStatus::Attention, Some("New ORDER entered on docket 12")),
+            vec!["digest", "compute-deadlines"]
+        );
+        assert_eq!(
+            chain.fired_by(RunStatus::Attention, Some("Docket unchanged")),
+            vec!["digest"]
+        );
+        assert_eq!(chain.fired_by(RunStatus::Ok, None), vec!["digest"]);
+        assert_eq!(
+            chain.fired_by(RunStatus::Failed, Some("PACER timeout")),
+            vec!["page-attorney"]
+        );
+        assert_eq!(
+            chain.targets(),
+            vec!["digest", "compute-deadlines", "page-attorney"]
+        );
+
+        let roundtrip: RoutineChain = serde_json::from_value(chain.to_json()).unwrap();
+        assert_eq!(roundtrip, chain);
+        assert!(
+            RoutineChain::default()
+                .to_json()
+                .as_object()
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_check_chain_rejects_cycles_and_unknown_targets() {
+        let monitor = chained("docket-monitor", &["compute-deadlines"]);
+        let deadlines = chained("compute-deadlines", &["calendar-sync"]);
+        let calendar = chained("calendar-sync", &[]);
+        let saved = vec![monitor.clone(), deadlines.clone(), calendar.clone()];
+        assert!(check_chain(&monitor, &saved).is_ok());
+
+        let mut looping = calendar.clone();
+        looping.chain.on_success = vec!["docket-monitor".to_string()];
+        let err = check_chain(&looping, &saved).unwrap_err().to_string();
+        assert!(
+            err.contains("calendar-sync -> docket-monitor -> compute-deadlines -> calendar-sync"),
+            "{err}"
+        );
+
+        let selfish = chained("self", &["self"]);
+        assert!(check_chain(&selfish, &[]).is_err());
+
+        let dangling = chained("orphan", &["missing"]);
+        let err = check_chain(&dangling, &saved).unwrap_err().to_string();
+        assert!(err.contains("no routine named 'missing'"), "{err}");
+
+        // A conditional edge closes a loop just like an unconditional one.
+        let mut conditional = calendar;
+        conditional.chain.on_condition = vec![ChainCondition {
+            routine: "compute-deadlines".to_string(),
+            status: vec![RunStatus::Attention],
+            pattern: Some("order".to_string()),
+        }];
+        assert!(check_chain(&conditional, &saved).is_err());
+
+        let mut bad_pattern = chained("bad", &[]);
+        bad_pattern.chain.on_condition = vec![ChainCondition {
+            routine: "calendar-sync".to_string(),
+            status: Vec::new(),
+            pattern: Some("(".to_string()),
+        }];
+        assert!(check_chain(&bad_pattern, &saved).is_err());
+    }
 }
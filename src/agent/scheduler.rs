@@ -8,6 +8,7 @@ use tokio::sync::{RwLock, mpsc, oneshot};
 use tokio::task::JoinHandle;
 use uuid::Uuid;
 
+use crate::agent::cost_guard::CostGuard;
 use crate::agent::task::{Task, TaskContext, TaskOutput};
 use crate::agent::worker::{Worker, WorkerDeps};
 use crate::config::AgentConfig;
@@ -52,6 +53,7 @@ pub struct Scheduler {
     tools: Arc<ToolRegistry>,
     store: Option<Arc<dyn Database>>,
     hooks: Arc<HookRegistry>,
+    cost_guard: Option<Arc<CostGuard>>,
     /// Running jobs (main LLM-driven jobs).
     jobs: Arc<RwLock<HashMap<Uuid, ScheduledJob>>>,
     /// Running sub-tasks (tool executions, background tasks).
@@ -80,11 +82,18 @@ impl Scheduler {
             tools,
             store,
             hooks,
+            cost_guard: None,
             jobs: Arc::new(RwLock::new(HashMap::new())),
             subtasks: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
+    /// Record job LLM and tool spend against the job's matter.
+    pub fn with_cost_guard(mut self, cost_guard: Arc<CostGuard>) -> Self {
+        self.cost_guard = Some(cost_guard);
+        self
+    }
+
     /// Create, persist, and schedule a job in one shot.
     ///
     /// This is the preferred entry point for dispatching new jobs. It:
@@ -171,6 +180,7 @@ impl Scheduler {
                 tools: self.tools.clone(),
                 store: self.store.clone(),
                 hooks: self.hooks.clone(),
+                cost_guard: self.cost_guard.clone(),
                 timeout: self.config.job_timeout,
                 use_planning: self.config.use_planning,
                 skeptical_mode_default: self.skeptical_mode_default,
@@ -191,6 +201,7 @@ impl Scheduler {
 
             // Insert while still holding the write lock
             jobs.insert(job_id, ScheduledJob { handle, tx });
+            crate::observability::metrics::set_jobs_running(jobs.len());
         }
 
         // Cleanup task for this job to avoid capacity leaks
@@ -206,7 +217,9 @@ impl Scheduler {
                 };
 
                 if finished {
-                    jobs.write().await.remove(&job_id);
+                    let mut jobs = jobs.write().await;
+                    jobs.remove(&job_id);
+                    crate::observability::metrics::set_jobs_running(jobs.len());
                     break;
                 }
 
@@ -457,6 +470,7 @@ impl Scheduler {
         let mut jobs = self.jobs.write().await;
 
         if let Some(scheduled) = jobs.remove(&job_id) {
+            crate::observability::metrics::set_jobs_running(jobs.len());
             // Send stop signal
             let _ = scheduled.tx.send(WorkerMessage::Stop).await;
 
@@ -544,6 +558,7 @@ impl Scheduler {
                 jobs.remove(&id);
                 tracing::debug!("Cleaned up finished job {}", id);
             }
+            crate::observability::metrics::set_jobs_running(jobs.len());
         }
 
         // Clean up subtasks
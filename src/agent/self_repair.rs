@@ -22,6 +22,9 @@ pub struct StuckJob {
     pub repair_attempts: u32,
 }
 
+/// Failures after which a tool counts as broken.
+pub const BROKEN_TOOL_FAILURE_THRESHOLD: i32 = 5;
+
 /// A tool that has been detected as broken.
 #[derive(Debug, Clone)]
 pub struct BrokenTool {
@@ -189,8 +192,7 @@ impl SelfRepair for DefaultSelfRepair {
             return vec![];
         };
 
-        // Threshold: 5 failures before considering a tool broken
-        match store.get_broken_tools(5).await {
+        match store.get_broken_tools(BROKEN_TOOL_FAILURE_THRESHOLD).await {
             Ok(tools) => {
                 if !tools.is_empty() {
                     tracing::info!("Detected {} broken tools needing repair", tools.len());
@@ -40,6 +40,15 @@ impl SubmissionParser {
         if lower == "/suggest" {
             return Submission::Suggest;
         }
+        if lower == "/file" || lower.starts_with("/file ") {
+            let mut parts = trimmed.split_whitespace().skip(1);
+            let matter_id = parts.next().map(|s| s.to_string());
+            let title = parts.collect::<Vec<_>>().join(" ");
+            return Submission::FileToMatter {
+                matter_id,
+                title: (!title.is_empty()).then_some(title),
+            };
+        }
         if lower == "/thread new" || lower == "/new" {
             return Submission::NewThread;
         }
@@ -229,6 +238,14 @@ pub enum Submission {
     /// Suggest next steps based on the current thread.
     Suggest,
 
+    /// File the latest exchange of the current thread into a matter as a memo.
+    FileToMatter {
+        /// Target matter; defaults to the active matter.
+        matter_id: Option<String>,
+        /// Memo title; defaults to the question's first line.
+        title: Option<String>,
+    },
+
     /// Quit the agent. Bypasses thread-state checks.
     Quit,
 
@@ -313,6 +330,7 @@ impl Submission {
                 | Self::Heartbeat
                 | Self::Summarize
                 | Self::Suggest
+                | Self::FileToMatter { .. }
                 | Self::SystemCommand { .. }
         )
     }
@@ -489,6 +507,29 @@ mod tests {
         assert!(matches!(submission, Submission::Suggest));
     }
 
+    #[test]
+    fn test_parser_file_to_matter() {
+        let submission = SubmissionParser::parse("/file");
+        assert!(matches!(
+            submission,
+            Submission::FileToMatter {
+                matter_id: None,
+                title: None
+            }
+        ));
+
+        let submission = SubmissionParser::parse("/FILE acme-v-foo Lease Renewal Options");
+        assert!(matches!(
+            submission,
+            Submission::FileToMatter { matter_id: Some(ref m), title: Some(ref t) }
+                if m == "acme-v-foo" && t == "Lease Renewal Options"
+        ));
+        assert!(submission.is_control());
+
+        let submission = SubmissionParser::parse("/files");
+        assert!(matches!(submission, Submission::UserInput { .. }));
+    }
+
     #[test]
     fn test_parser_invalid_commands_become_user_input() {
         // Invalid UUID should become user input
@@ -0,0 +1,215 @@
+//! Per-thread configuration.
+//!
+//! A thread can carry its own system prompt additions, a response tone, and
+//! an allow-list of tools, e.g. a drafting thread with citation discipline
+//! instructions next to a quick-questions thread with no document tools.
+//! The configuration lives under [`THREAD_CONFIG_METADATA_KEY`] in the
+//! conversation's metadata and is applied on every turn of that thread.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::Database;
+use crate::llm::ToolDefinition;
+
+/// Conversation metadata key holding the thread configuration.
+pub const THREAD_CONFIG_METADATA_KEY: &str = "thread_config";
+
+/// Longest system prompt addition accepted for a thread.
+pub const MAX_SYSTEM_PROMPT_ADDITIONS_CHARS: usize = 4_000;
+
+/// Most tools a thread's allow-list may name.
+pub const MAX_ENABLED_TOOLS: usize = 100;
+
+/// Register the assistant writes in for a thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThreadTone {
+    Formal,
+    Plain,
+    Concise,
+    Detailed,
+}
+
+impl ThreadTone {
+    pub const ALL: [Self; 4] = [Self::Formal, Self::Plain, Self::Concise, Self::Detailed];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Formal => "formal",
+            Self::Plain => "plain",
+            Self::Concise => "concise",
+            Self::Detailed => "detailed",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        Self::ALL
+            .into_iter()
+            .find(|tone| tone.as_str().eq_ignore_ascii_case(raw))
+    }
+
+    fn instruction(self) -> &'static str {
+        match self {
+            Self::Formal => {
+                "Write in a formal, professional register suitable for correspondence with \
+                 counsel or a court."
+            }
+            Self::Plain => {
+                "Write in plain language: short sentences, everyday words, and no legal jargon \
+                 without an explanation."
+            }
+            Self::Concise => {
+                "Be concise: lead with the answer and keep supporting detail to what the \
+                 question needs."
+            }
+            Self::Detailed => {
+                "Be thorough: set out the reasoning, authorities, assumptions, and open \
+                 questions in full."
+            }
+        }
+    }
+}
+
+/// Configuration applied to every turn of one thread.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreadConfig {
+    /// Instructions appended to the system prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt_additions: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tone: Option<ThreadTone>,
+    /// Tools the thread may use; `None` enables every registered tool.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled_tools: Option<Vec<String>>,
+}
+
+impl ThreadConfig {
+    /// Read the configuration from conversation metadata. Missing or
+    /// malformed entries yield `None`.
+    pub fn from_metadata(metadata: &serde_json::Value) -> Option<Self> {
+        let raw = metadata
+            .get(THREAD_CONFIG_METADATA_KEY)
+            .filter(|v| !v.is_null())?;
+        match serde_json::from_value::<Self>(raw.clone()) {
+            Ok(config) if !config.is_empty() => Some(config),
+            Ok(_) => None,
+            Err(e) => {
+                tracing::warn!("Ignoring malformed thread configuration: {}", e);
+                None
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.system_prompt_additions.is_none()
+            && self.tone.is_none()
+            && self.enabled_tools.is_none()
+    }
+
+    /// System prompt section for this thread, or `None` when the thread
+    /// adds nothing.
+    pub fn prompt_addendum(&self) -> Option<String> {
+        let mut lines = Vec::new();
+        if let Some(tone) = self.tone {
+            lines.push(tone.instruction().to_string());
+        }
+        if let Some(additions) = self
+            .system_prompt_additions
+            .as_deref()
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+        {
+            lines.push(additions.to_string());
+        }
+        if lines.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "## Thread Instructions\n\nThe user configured this conversation thread as \
+             follows.\n\n{}",
+            lines.join("\n\n")
+        ))
+    }
+
+    /// Whether the thread may call `tool_name`.
+    pub fn allows_tool(&self, tool_name: &str) -> bool {
+        self.enabled_tools
+            .as_ref()
+            .is_none_or(|tools| tools.iter().any(|t| t == tool_name))
+    }
+
+    /// Drop definitions of tools the thread has not enabled.
+    pub fn filter_tools(&self, tool_defs: Vec<ToolDefinition>) -> Vec<ToolDefinition> {
+        if self.enabled_tools.is_none() {
+            return tool_defs;
+        }
+        tool_defs
+            .into_iter()
+            .filter(|def| self.allows_tool(&def.name))
+            .collect()
+    }
+}
+
+/// Load the configuration stored for `thread_id`, if any. Lookup failures
+/// are logged and treated as no configuration.
+pub async fn load_thread_config(store: &dyn Database, thread_id: Uuid) -> Option<ThreadConfig> {
+    match store.get_conversation_metadata(thread_id).await {
+        Ok(metadata) => metadata.as_ref().and_then(ThreadConfig::from_metadata),
+        Err(e) => {
+            tracing::debug!("Could not load thread configuration: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn def(name: &str) -> ToolDefinition {
+        ToolDefinition {
+            name: name.to_string(),
+            description: String::new(),
+            parameters: serde_json::json!({}),
+        }
+    }
+
+    #[test]
+    fn config_reads_from_metadata_and_builds_prompt_section() {
+        let metadata = serde_json::json!({
+            "thread_type": "thread",
+            "thread_config": {
+                "system_prompt_additions": "Cite a pinpoint for every authority.",
+                "tone": "formal",
+                "enabled_tools": ["memory_search", "draft_check"],
+            },
+        });
+        let config = ThreadConfig::from_metadata(&metadata).expect("config");
+        assert_eq!(config.tone, Some(ThreadTone::Formal));
+        let addendum = config.prompt_addendum().expect("addendum");
+        assert!(addendum.starts_with("## Thread Instructions"));
+        assert!(addendum.contains("formal, professional register"));
+        assert!(addendum.ends_with("Cite a pinpoint for every authority."));
+
+        let names: Vec<String> = config
+            .filter_tools(vec![def("memory_search"), def("shell"), def("draft_check")])
+            .into_iter()
+            .map(|d| d.name)
+            .collect();
+        assert_eq!(names, vec!["memory_search", "draft_check"]);
+        assert!(!config.allows_tool("shell"));
+
+        assert_eq!(
+            ThreadConfig::from_metadata(&serde_json::json!({"thread_config": {"tone": "shouty"}})),
+            None
+        );
+        assert_eq!(
+            ThreadConfig::from_metadata(&serde_json::json!({"thread_type": "thread"})),
+            None
+        );
+        assert!(ThreadConfig::default().allows_tool("shell"));
+        assert_eq!(ThreadConfig::default().prompt_addendum(), None);
+    }
+}
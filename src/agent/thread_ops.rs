@@ -12,13 +12,11 @@ use uuid::Uuid;
 use crate::agent::Agent;
 use crate::agent::compaction::{ContextCompactor, MatterCompactionScope};
 use crate::agent::dispatcher::{
-    AgenticLoopResult, ToolAuditContext, check_auth_required, execute_chat_tool_standalone,
-    parse_auth_result,
+    AgenticLoopResult, check_auth_required, execute_chat_tool_standalone, parse_auth_result,
 };
 use crate::agent::session::{PendingApproval, Session, ThreadState};
 use crate::agent::submission::SubmissionResult;
 use crate::channels::{IncomingMessage, StatusUpdate};
-use crate::context::JobContext;
 use crate::error::Error;
 use crate::llm::ChatMessage;
 use crate::tools::ApprovalRequirement;
@@ -1046,8 +1044,7 @@ impl Agent {
             }
 
             // Execute the approved tool and continue the loop
-            let job_ctx =
-                JobContext::with_user(&message.user_id, "chat", "Interactive chat session");
+            let job_ctx = Self::chat_job_context(message, &effective_legal_config);
 
             let _ = self
                 .channels
@@ -1065,9 +1062,7 @@ impl Agent {
                     &pending.tool_name,
                     &pending.parameters,
                     &job_ctx,
-                    Some(ToolAuditContext {
-                        thread_id: thread_id.to_string(),
-                    }),
+                    Some(self.tool_audit_context(thread_id, message, &effective_legal_config)),
                 )
                 .await;
 
@@ -1184,9 +1179,8 @@ impl Agent {
                 crate::llm::ToolCall,
                 Arc<dyn crate::tools::Tool>,
             )> = None;
-            let tool_audit_ctx = ToolAuditContext {
-                thread_id: thread_id.to_string(),
-            };
+            let tool_audit_ctx =
+                self.tool_audit_context(thread_id, message, &effective_legal_config);
 
             for (idx, tc) in deferred_tool_calls.iter().enumerate() {
                 if let Some(tool) = self.tools().get(&tc.name).await {
@@ -1920,6 +1914,7 @@ mod tests {
             legal_config: legal,
             hooks: Arc::new(crate::hooks::HookRegistry::new()),
             cost_guard: Arc::new(CostGuard::new(CostGuardConfig::default())),
+            routine_engine_slot: None,
         };
 
         Agent::new(
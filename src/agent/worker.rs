@@ -7,6 +7,7 @@ use tokio::sync::mpsc;
 use tokio::task::JoinSet;
 use uuid::Uuid;
 
+use crate::agent::cost_guard::{CostAttribution, CostGuard};
 use crate::agent::scheduler::WorkerMessage;
 use crate::agent::task::TaskOutput;
 use crate::context::{ContextManager, JobContext, JobState};
@@ -32,6 +33,7 @@ pub struct WorkerDeps {
     pub tools: Arc<ToolRegistry>,
     pub store: Option<Arc<dyn Database>>,
     pub hooks: Arc<HookRegistry>,
+    pub cost_guard: Option<Arc<CostGuard>>,
     pub timeout: Duration,
     pub use_planning: bool,
     pub skeptical_mode_default: bool,
@@ -55,8 +57,6 @@ struct SkepticalModeContext {
     matter_id: Option<String>,
 }
 
-const LEGAL_ACTIVE_MATTER_SETTING_KEY: &str = "legal.active_matter";
-
 fn matter_id_from_job_metadata(job_ctx: &JobContext) -> Option<String> {
     crate::legal::policy::matter_id_from_metadata(&job_ctx.metadata).or_else(|| {
         job_ctx
@@ -67,6 +67,25 @@ fn matter_id_from_job_metadata(job_ctx: &JobContext) -> Option<String> {
     })
 }
 
+/// Job spend is billed to the job's matter, else the owner's active matter.
+async fn job_cost_attribution(
+    deps: &WorkerDeps,
+    job_id: Uuid,
+    job_ctx: &JobContext,
+) -> CostAttribution {
+    let matter_id = match matter_id_from_job_metadata(job_ctx) {
+        Some(matter_id) => Some(matter_id),
+        None => match deps.store.as_ref() {
+            Some(store) => {
+                crate::agent::cost_guard::active_matter_for_user(store.as_ref(), &job_ctx.user_id)
+                    .await
+            }
+            None => None,
+        },
+    };
+    CostAttribution::new(&job_ctx.user_id, matter_id).with_job(job_id)
+}
+
 fn build_worker_system_prompt(
     title: &str,
     description: &str,
@@ -146,20 +165,137 @@ impl Worker {
     }
 
     async fn load_active_matter_for_user(&self, user_id: &str) -> Option<String> {
-        let store = self.store()?;
-        let setting = store
-            .get_setting(user_id, LEGAL_ACTIVE_MATTER_SETTING_KEY)
+        crate::agent::cost_guard::active_matter_for_user(self.store()?.as_ref(), user_id).await
+    }
+
+    /// Correct the plan's estimates with the forecast for the job's category
+    /// and snapshot the raw estimate so the job's actuals can refine later
+    /// forecasts.
+    async fn record_plan_estimate(&self, plan: &ActionPlan) {
+        let (Some(raw_cost), Some(raw_secs)) = (plan.estimated_cost, plan.estimated_time_secs)
+        else {
+            return;
+        };
+        let Ok(job_ctx) = self.context_manager().get_context(self.job_id).await else {
+            return;
+        };
+        let category = job_ctx
+            .category
+            .clone()
+            .unwrap_or_else(|| "general".to_string());
+        let raw_cost = rust_decimal::Decimal::try_from(raw_cost)
+            .unwrap_or_default()
+            .max(rust_decimal::Decimal::ZERO);
+        let raw_time = Duration::from_secs(raw_secs);
+
+        let mut snapshot_id = None;
+        let mut forecaster = crate::estimation::EstimationForecaster::default();
+        if let Some(store) = self.store() {
+            match store
+                .list_estimation_samples(Some(&category), crate::estimation::FORECAST_HISTORY_LIMIT)
+                .await
+            {
+                Ok(samples) => forecaster = crate::estimation::EstimationForecaster::fit(&samples),
+                Err(e) => tracing::warn!(
+                    "Failed to load estimation history for job {}: {}",
+                    self.job_id,
+                    e
+                ),
+            }
+            let tool_names: Vec<String> =
+                plan.actions.iter().map(|a| a.tool_name.clone()).collect();
+            let value =
+                crate::estimation::ValueEstimator::new().estimate(&job_ctx.description, raw_cost);
+            match store
+                .save_estimation_snapshot(
+                    self.job_id,
+                    &category,
+                    &tool_names,
+                    raw_cost,
+                    i32::try_from(raw_secs).unwrap_or(i32::MAX),
+                    value,
+                )
+                .await
+            {
+                Ok(id) => snapshot_id = Some(id),
+                Err(e) => tracing::warn!(
+                    "Failed to save estimation snapshot for job {}: {}",
+                    self.job_id,
+                    e
+                ),
+            }
+        }
+
+        let (cost, duration) = forecaster.correct(&category, raw_cost, raw_time);
+        let _ = self
+            .context_manager()
+            .update_context(self.job_id, |ctx| {
+                ctx.estimated_cost = Some(cost);
+                ctx.estimated_duration = Some(duration);
+                if let Some(id) = snapshot_id {
+                    if !ctx.metadata.is_object() {
+                        ctx.metadata = serde_json::json!({});
+                    }
+                    ctx.metadata["estimation_snapshot_id"] = serde_json::json!(id.to_string());
+                }
+            })
+            .await;
+    }
+
+    /// Fill in the actual cost and duration on the job's estimation snapshot.
+    async fn record_estimation_actuals(&self) {
+        let Some(store) = self.store() else {
+            return;
+        };
+        let Ok(job_ctx) = self.context_manager().get_context(self.job_id).await else {
+            return;
+        };
+        let Some(snapshot_id) = job_ctx
+            .metadata
+            .get("estimation_snapshot_id")
+            .and_then(|v| v.as_str())
+            .and_then(|v| Uuid::parse_str(v).ok())
+        else {
+            return;
+        };
+        let actual_secs = job_ctx.elapsed().map_or(0, |d| d.as_secs());
+        if let Err(e) = store
+            .update_estimation_actuals(
+                snapshot_id,
+                job_ctx.actual_cost,
+                i32::try_from(actual_secs).unwrap_or(i32::MAX),
+                None,
+            )
             .await
-            .ok()??;
-        let raw = setting.as_str()?;
-        let sanitized = crate::legal::policy::sanitize_matter_id(raw);
-        if sanitized.is_empty() {
-            None
-        } else {
-            Some(sanitized)
+        {
+            tracing::warn!(
+                "Failed to record estimation actuals for job {}: {}",
+                self.job_id,
+                e
+            );
         }
     }
 
+    /// Record an LLM call made by this job.
+    async fn record_llm_usage(&self, usage: &crate::llm::TokenUsage) {
+        let Some(cost_guard) = self.deps.cost_guard.as_ref() else {
+            return;
+        };
+        let Ok(job_ctx) = self.context_manager().get_context(self.job_id).await else {
+            return;
+        };
+        let attribution = job_cost_attribution(&self.deps, self.job_id, &job_ctx).await;
+        cost_guard
+            .record_llm_call(
+                &self.llm().active_model_name(),
+                usage.input_tokens,
+                usage.output_tokens,
+                Some(self.llm().cost_per_token()),
+                Some(&attribution),
+            )
+            .await;
+    }
+
     async fn record_skeptical_mode_audit_event(&self, context: &SkepticalModeContext) {
         if !context.enabled {
             return;
@@ -325,6 +461,7 @@ impl Worker {
                                 .map(|(i, a)| format!("{}. {} - {}", i + 1, a.tool_name, a.reasoning))
                                 .collect::<Vec<_>>().join("\n"))
                     }));
+                    self.record_plan_estimate(&p).await;
 
                     Some(p)
                 }
@@ -387,6 +524,7 @@ impl Worker {
             if selections.is_empty() {
                 // No tools from select_tools, ask LLM directly (may still return tool calls)
                 let respond_output = reasoning.respond_with_tools(reason_ctx).await?;
+                self.record_llm_usage(&respond_output.usage).await;
 
                 match respond_output.result {
                     RespondResult::Text(response) => {
@@ -720,6 +858,15 @@ impl Worker {
             }
         }
 
+        if let (Some(cost_guard), Ok(Ok(output))) = (deps.cost_guard.as_ref(), &result)
+            && let Some(cost) = output.cost
+        {
+            let attribution = job_cost_attribution(deps, job_id, &job_ctx).await;
+            cost_guard
+                .record_tool_cost(tool_name, cost, Some(&attribution))
+                .await;
+        }
+
         // Record action in memory and get the ActionRecord for persistence
         let action = match &result {
             Ok(Ok(output)) => {
@@ -1014,6 +1161,7 @@ impl Worker {
                 id: self.job_id,
                 reason: s,
             })?;
+        self.record_estimation_actuals().await;
 
         self.log_event(
             "result",
@@ -1180,6 +1328,7 @@ mod tests {
             tools: Arc::new(registry),
             store: None,
             hooks: Arc::new(crate::hooks::HookRegistry::new()),
+            cost_guard: None,
             timeout: Duration::from_secs(30),
             use_planning: false,
             skeptical_mode_default: false,
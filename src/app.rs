@@ -145,9 +145,17 @@ impl AppBuilder {
                     LibSqlBackend::new_remote_replica(db_path, url, token.expose_secret()).await?
                 } else {
                     LibSqlBackend::new_local(db_path).await?
-                };
+                }
+                .with_content_encryption_from_config(
+                    self.config.database.libsql_encrypt_content,
+                    self.config.secrets.master_key(),
+                )?;
                 backend.run_migrations().await?;
-                tracing::info!("libSQL database connected and migrations applied");
+                backend.encrypt_existing_content().await?;
+                tracing::info!(
+                    encrypted_content = backend.content_encryption_enabled(),
+                    "libSQL database connected and migrations applied"
+                );
 
                 #[cfg(feature = "libsql")]
                 {
@@ -298,13 +306,24 @@ impl AppBuilder {
     /// Phase 3: Initialize LLM provider chain.
     ///
     /// Delegates to `build_provider_chain` which applies all decorators
-    /// (retry, smart routing, failover, circuit breaker, response cache).
+    /// (retry, smart routing, failover, circuit breaker, response cache),
+    /// then layers the legal per-task routes on top.
     #[allow(clippy::type_complexity)]
     pub fn init_llm(
         &self,
     ) -> Result<(Arc<dyn LlmProvider>, Option<Arc<dyn LlmProvider>>), anyhow::Error> {
+        let cache_store = self
+            .db
+            .clone()
+            .map(|db| db as Arc<dyn crate::db::LlmResponseCacheStore>);
         let (llm, cheap_llm) =
-            crate::llm::build_provider_chain(&self.config.llm, self.session.clone())?;
+            crate::llm::build_provider_chain(&self.config.llm, self.session.clone(), cache_store)?;
+        let llm = crate::llm::apply_task_routing(
+            llm,
+            &self.config.llm,
+            &self.config.legal.llm_routing,
+            self.session.clone(),
+        )?;
         Ok((llm, cheap_llm))
     }
 
@@ -402,6 +421,18 @@ impl AppBuilder {
             }
             let ws = Arc::new(ws);
             tools.register_memory_tools(Arc::clone(&ws));
+            tools.register_translation_tool(Arc::clone(&ws), llm.clone(), Some(db.clone()));
+            tools.register_transcript_tool(Arc::clone(&ws));
+            tools.register_draft_check_tool(Arc::clone(&ws));
+            tools.register_plain_language_tool(Arc::clone(&ws), llm.clone(), Some(db.clone()));
+            tools.register_compare_documents_tool(Arc::clone(&ws), Some(db.clone()));
+            tools.register_damages_tool(Arc::clone(&ws));
+            tools.register_fact_extraction_tool(Arc::clone(&ws), db.clone(), llm.clone());
+            tools.register_status_report_tool(Arc::clone(&ws), db.clone());
+            tools.register_generate_document_tool(Arc::clone(&ws), db.clone());
+            tools.register_precedent_tool(Arc::clone(&ws), db.clone());
+            tools.register_document_tag_tool(Arc::clone(&ws), db.clone());
+            tools.register_compose_email_tool(Arc::clone(&ws));
             Some(ws)
         } else {
             None
@@ -548,7 +579,19 @@ impl AppBuilder {
                                     let has_tokens =
                                         is_authenticated(&server, &secrets, "default").await;
 
-                                    let client = if has_tokens || server.requires_auth() {
+                                    let client = if server.is_stdio() {
+                                        match McpClient::connect_stdio(server).await {
+                                            Ok(client) => client,
+                                            Err(e) => {
+                                                tracing::warn!(
+                                                    "Failed to start MCP server '{}': {}",
+                                                    server_name,
+                                                    e
+                                                );
+                                                return;
+                                            }
+                                        }
+                                    } else if has_tokens || server.requires_auth() {
                                         McpClient::new_authenticated(
                                             server, mcp_sm, secrets, "default",
                                         )
@@ -820,18 +863,14 @@ impl AppBuilder {
             }
 
             if embeddings.is_some() {
-                let ws_bg = Arc::clone(ws);
-                tokio::spawn(async move {
-                    match ws_bg.backfill_embeddings().await {
-                        Ok(count) if count > 0 => {
-                            tracing::info!("Backfilled embeddings for {} chunks", count);
-                        }
-                        Ok(_) => {}
-                        Err(e) => {
-                            tracing::warn!("Failed to backfill embeddings: {}", e);
-                        }
-                    }
-                });
+                let mut worker = crate::workspace::backfill::EmbeddingBackfillWorker::new(
+                    Arc::clone(ws),
+                    self.config.embeddings.backfill_config(),
+                );
+                if let Some(ref db) = self.db {
+                    worker = worker.with_store(Arc::clone(db));
+                }
+                worker.spawn();
             }
         }
 
@@ -858,12 +897,15 @@ impl AppBuilder {
         };
 
         let context_manager = Arc::new(ContextManager::new(self.config.agent.max_parallel_jobs));
-        let cost_guard = Arc::new(crate::agent::cost_guard::CostGuard::new(
-            crate::agent::cost_guard::CostGuardConfig {
+        let mut cost_guard =
+            crate::agent::cost_guard::CostGuard::new(crate::agent::cost_guard::CostGuardConfig {
                 max_cost_per_day_cents: self.config.agent.max_cost_per_day_cents,
                 max_actions_per_hour: self.config.agent.max_actions_per_hour,
-            },
-        ));
+            });
+        if let Some(db) = self.db.as_ref() {
+            cost_guard = cost_guard.with_ledger(Arc::clone(db));
+        }
+        let cost_guard = Arc::new(cost_guard);
 
         tracing::info!(
             "Tool registry initialized with {} total tools",
@@ -6,18 +6,19 @@ use async_trait::async_trait;
 use axum::{
     Json, Router,
     extract::{DefaultBodyLimit, State},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
     routing::{get, post},
 };
 use secrecy::ExposeSecret;
 use serde::{Deserialize, Serialize};
 use subtle::ConstantTimeEq;
 use tokio::sync::{RwLock, mpsc, oneshot};
-use tokio_stream::wrappers::ReceiverStream;
 use uuid::Uuid;
 
-use crate::channels::{Channel, IncomingMessage, MessageStream, OutgoingResponse};
+use crate::channels::{
+    Channel, INTAKE_RETRY_AFTER, IncomingMessage, IntakeQueue, MessageStream, OutgoingResponse,
+};
 use crate::config::HttpConfig;
 use crate::error::ChannelError;
 
@@ -30,6 +31,8 @@ pub struct HttpChannel {
 struct HttpChannelState {
     /// Sender for incoming messages.
     tx: RwLock<Option<mpsc::Sender<IncomingMessage>>>,
+    /// Admission control in front of `tx`.
+    intake: Arc<IntakeQueue>,
     /// Pending responses keyed by message ID.
     pending_responses: RwLock<std::collections::HashMap<Uuid, oneshot::Sender<String>>>,
     /// Expected webhook secret for authentication (if configured).
@@ -71,6 +74,7 @@ impl HttpChannel {
             config,
             state: Arc::new(HttpChannelState {
                 tx: RwLock::new(None),
+                intake: Arc::new(IntakeQueue::default()),
                 pending_responses: RwLock::new(std::collections::HashMap::new()),
                 webhook_secret,
                 user_id,
@@ -143,6 +147,21 @@ async fn health_handler() -> impl IntoResponse {
 async fn webhook_handler(
     State(state): State<Arc<HttpChannelState>>,
     Json(req): Json<WebhookRequest>,
+) -> Response {
+    let (status, body) = handle_webhook(state, req).await;
+    let mut response = (status, body).into_response();
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        response.headers_mut().insert(
+            header::RETRY_AFTER,
+            HeaderValue::from(INTAKE_RETRY_AFTER.as_secs()),
+        );
+    }
+    response
+}
+
+async fn handle_webhook(
+    state: Arc<HttpChannelState>,
+    req: WebhookRequest,
 ) -> (StatusCode, Json<WebhookResponse>) {
     // Rate limiting
     {
@@ -267,16 +286,21 @@ async fn process_message(
             }),
         );
     };
-    if tx.send(msg).await.is_err() {
+    if let Err(err) = state.intake.submit(Some(&tx), msg) {
         if wait_for_response {
             let _ = state.pending_responses.write().await.remove(&msg_id);
         }
+        let status = if err.retry_after().is_some() {
+            StatusCode::TOO_MANY_REQUESTS
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        };
         return (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            status,
             Json(WebhookResponse {
                 message_id: msg_id,
                 status: "error".to_string(),
-                response: Some("Channel closed".to_string()),
+                response: Some(err.to_string()),
             }),
         );
     }
@@ -321,7 +345,7 @@ impl Channel for HttpChannel {
             });
         }
 
-        let (tx, rx) = mpsc::channel(256);
+        let (tx, stream) = self.state.intake.channel();
         *self.state.tx.write().await = Some(tx);
 
         tracing::info!(
@@ -330,7 +354,7 @@ impl Channel for HttpChannel {
             self.config.port
         );
 
-        Ok(Box::pin(ReceiverStream::new(rx)))
+        Ok(stream)
     }
 
     async fn respond(
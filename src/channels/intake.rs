@@ -0,0 +1,270 @@
+//! Bounded message intake with backpressure.
+//!
+//! Channels used to `send().await` into their agent-loop mpsc channel, so a
+//! burst of traffic either parked request handlers indefinitely or, for
+//! large buffers, piled messages up in memory. [`IntakeQueue`] instead admits
+//! messages with `try_send` and rejects them outright when the queue is full
+//! or when one user already has too many messages waiting, so callers can
+//! answer 429 with a `Retry-After` hint and interactive users keep their
+//! share of the queue.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use futures::StreamExt;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::channels::{IncomingMessage, MessageStream};
+
+/// Default number of messages a channel buffers for the agent loop.
+pub const DEFAULT_INTAKE_CAPACITY: usize = 256;
+
+/// Default number of queued messages allowed per user.
+pub const DEFAULT_PER_USER_IN_FLIGHT: usize = 32;
+
+/// Retry hint returned with every rejection.
+pub const INTAKE_RETRY_AFTER: Duration = Duration::from_secs(5);
+
+/// Intake limits for one channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntakeConfig {
+    /// Queue capacity shared by all users of the channel.
+    pub capacity: usize,
+    /// Messages one user may have queued but not yet picked up.
+    pub per_user_in_flight: usize,
+}
+
+impl Default for IntakeConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_INTAKE_CAPACITY,
+            per_user_in_flight: DEFAULT_PER_USER_IN_FLIGHT,
+        }
+    }
+}
+
+/// Why a message was not admitted.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum IntakeError {
+    #[error("Message queue is full. Retry in {}s.", .retry_after.as_secs())]
+    QueueFull { retry_after: Duration },
+
+    #[error("Too many messages in flight for this user. Retry in {}s.", .retry_after.as_secs())]
+    UserLimit { retry_after: Duration },
+
+    #[error("Channel not started")]
+    NotStarted,
+
+    #[error("Channel closed")]
+    Closed,
+}
+
+impl IntakeError {
+    /// Retry hint for backpressure rejections; `None` for hard failures.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::QueueFull { retry_after } | Self::UserLimit { retry_after } => Some(*retry_after),
+            Self::NotStarted | Self::Closed => None,
+        }
+    }
+}
+
+/// Point-in-time intake metrics.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct IntakeStats {
+    /// Messages waiting for the agent loop.
+    pub depth: usize,
+    pub capacity: usize,
+    pub per_user_in_flight: usize,
+    /// Users with at least one queued message.
+    pub active_users: usize,
+    /// Highest in-flight count held by a single user.
+    pub max_user_in_flight: usize,
+    pub accepted: u64,
+    pub rejected_queue_full: u64,
+    pub rejected_user_limit: u64,
+}
+
+/// Admission control in front of a channel's agent-loop sender.
+///
+/// Per-user counts are held from admission until the agent loop pulls the
+/// message off the stream returned by [`IntakeQueue::stream`].
+#[derive(Debug)]
+pub struct IntakeQueue {
+    config: IntakeConfig,
+    in_flight: Mutex<HashMap<String, usize>>,
+    accepted: AtomicU64,
+    rejected_queue_full: AtomicU64,
+    rejected_user_limit: AtomicU64,
+}
+
+impl IntakeQueue {
+    pub fn new(config: IntakeConfig) -> Self {
+        Self {
+            config: IntakeConfig {
+                capacity: config.capacity.max(1),
+                per_user_in_flight: config.per_user_in_flight.max(1),
+            },
+            in_flight: Mutex::new(HashMap::new()),
+            accepted: AtomicU64::new(0),
+            rejected_queue_full: AtomicU64::new(0),
+            rejected_user_limit: AtomicU64::new(0),
+        }
+    }
+
+    pub fn config(&self) -> IntakeConfig {
+        self.config
+    }
+
+    /// Create the bounded channel backing this queue. The receiver half is
+    /// wrapped so that dequeuing a message releases its user's slot.
+    pub fn channel(self: &Arc<Self>) -> (mpsc::Sender<IncomingMessage>, MessageStream) {
+        let (tx, rx) = mpsc::channel(self.config.capacity);
+        (tx, self.stream(rx))
+    }
+
+    /// Wrap a receiver so each dequeued message releases its user's slot.
+    pub fn stream(self: &Arc<Self>, rx: mpsc::Receiver<IncomingMessage>) -> MessageStream {
+        let queue = Arc::clone(self);
+        Box::pin(ReceiverStream::new(rx).map(move |msg| {
+            queue.release(&msg.user_id);
+            msg
+        }))
+    }
+
+    /// Admit `msg` into `tx` without waiting.
+    pub fn submit(
+        &self,
+        tx: Option<&mpsc::Sender<IncomingMessage>>,
+        msg: IncomingMessage,
+    ) -> Result<(), IntakeError> {
+        let tx = tx.ok_or(IntakeError::NotStarted)?;
+        // Held across `try_send` so a concurrent dequeue cannot release the
+        // slot before it is counted.
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        let held = in_flight.get(&msg.user_id).copied().unwrap_or(0);
+        if held >= self.config.per_user_in_flight {
+            self.rejected_user_limit.fetch_add(1, Ordering::Relaxed);
+            return Err(IntakeError::UserLimit {
+                retry_after: INTAKE_RETRY_AFTER,
+            });
+        }
+        let user_id = msg.user_id.clone();
+        match tx.try_send(msg) {
+            Ok(()) => {
+                *in_flight.entry(user_id).or_insert(0) += 1;
+                self.accepted.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                self.rejected_queue_full.fetch_add(1, Ordering::Relaxed);
+                tracing::warn!(user_id = %user_id, "Message intake queue full, rejecting");
+                Err(IntakeError::QueueFull {
+                    retry_after: INTAKE_RETRY_AFTER,
+                })
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => Err(IntakeError::Closed),
+        }
+    }
+
+    fn release(&self, user_id: &str) {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(count) = in_flight.get_mut(user_id) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                in_flight.remove(user_id);
+            }
+        }
+    }
+
+    /// Current metrics; `tx` supplies the live queue depth.
+    pub fn stats(&self, tx: Option<&mpsc::Sender<IncomingMessage>>) -> IntakeStats {
+        let in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        IntakeStats {
+            depth: tx.map_or(0, |tx| tx.max_capacity() - tx.capacity()),
+            capacity: self.config.capacity,
+            per_user_in_flight: self.config.per_user_in_flight,
+            active_users: in_flight.len(),
+            max_user_in_flight: in_flight.values().copied().max().unwrap_or(0),
+            accepted: self.accepted.load(Ordering::Relaxed),
+            rejected_queue_full: self.rejected_queue_full.load(Ordering::Relaxed),
+            rejected_user_limit: self.rejected_user_limit.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for IntakeQueue {
+    fn default() -> Self {
+        Self::new(IntakeConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(user: &str) -> IncomingMessage {
+        IncomingMessage::new("gateway", user, "hi")
+    }
+
+    #[tokio::test]
+    async fn rejects_when_queue_is_full() {
+        let queue = Arc::new(IntakeQueue::new(IntakeConfig {
+            capacity: 2,
+            per_user_in_flight: 10,
+        }));
+        let (tx, mut stream) = queue.channel();
+        queue.submit(Some(&tx), msg("a")).unwrap();
+        queue.submit(Some(&tx), msg("b")).unwrap();
+        let err = queue.submit(Some(&tx), msg("c")).unwrap_err();
+        assert_eq!(err.retry_after(), Some(INTAKE_RETRY_AFTER));
+        assert!(matches!(err, IntakeError::QueueFull { .. }));
+
+        let stats = queue.stats(Some(&tx));
+        assert_eq!(
+            (stats.depth, stats.accepted, stats.rejected_queue_full),
+            (2, 2, 1)
+        );
+
+        stream.next().await.unwrap();
+        queue.submit(Some(&tx), msg("c")).unwrap();
+    }
+
+    #[tokio::test]
+    async fn per_user_limit_leaves_room_for_other_users() {
+        let queue = Arc::new(IntakeQueue::new(IntakeConfig {
+            capacity: 10,
+            per_user_in_flight: 2,
+        }));
+        let (tx, mut stream) = queue.channel();
+        queue.submit(Some(&tx), msg("webhook")).unwrap();
+        queue.submit(Some(&tx), msg("webhook")).unwrap();
+        assert!(matches!(
+            queue.submit(Some(&tx), msg("webhook")),
+            Err(IntakeError::UserLimit { .. })
+        ));
+        queue.submit(Some(&tx), msg("alice")).unwrap();
+
+        let stats = queue.stats(Some(&tx));
+        assert_eq!(stats.active_users, 2);
+        assert_eq!(stats.max_user_in_flight, 2);
+        assert_eq!(stats.rejected_user_limit, 1);
+
+        assert_eq!(stream.next().await.unwrap().user_id, "webhook");
+        queue.submit(Some(&tx), msg("webhook")).unwrap();
+    }
+
+    #[test]
+    fn reports_unstarted_and_closed_channels() {
+        let queue = IntakeQueue::default();
+        assert_eq!(queue.submit(None, msg("a")), Err(IntakeError::NotStarted));
+        let (tx, rx) = mpsc::channel(1);
+        drop(rx);
+        assert_eq!(queue.submit(Some(&tx), msg("a")), Err(IntakeError::Closed));
+        assert_eq!(queue.stats(None).active_users, 0);
+    }
+}
@@ -6,6 +6,7 @@ use std::sync::Arc;
 use futures::stream;
 use tokio::sync::{RwLock, mpsc};
 
+use crate::channels::review::ReviewQueue;
 use crate::channels::{Channel, IncomingMessage, MessageStream, OutgoingResponse, StatusUpdate};
 use crate::error::ChannelError;
 
@@ -18,19 +19,28 @@ pub struct ChannelManager {
     inject_tx: mpsc::Sender<IncomingMessage>,
     /// Taken once in `start_all()` and merged into the stream.
     inject_rx: tokio::sync::Mutex<Option<mpsc::Receiver<IncomingMessage>>>,
+    /// Supervised-mode hold for outbound messages to external channels.
+    review: Arc<ReviewQueue>,
 }
 
 impl ChannelManager {
     /// Create a new channel manager.
     pub fn new() -> Self {
         let (inject_tx, inject_rx) = mpsc::channel(64);
+        let channels = Arc::new(RwLock::new(HashMap::new()));
         Self {
-            channels: Arc::new(RwLock::new(HashMap::new())),
+            review: Arc::new(ReviewQueue::new(Arc::clone(&channels))),
+            channels,
             inject_tx,
             inject_rx: tokio::sync::Mutex::new(Some(inject_rx)),
         }
     }
 
+    /// Review queue that holds outbound messages while supervised mode is on.
+    pub fn review_queue(&self) -> Arc<ReviewQueue> {
+        Arc::clone(&self.review)
+    }
+
     /// Get a clone of the injection sender.
     ///
     /// Background tasks (like job monitors) use this to push messages into the
@@ -121,15 +131,23 @@ impl ChannelManager {
         msg: &IncomingMessage,
         response: OutgoingResponse,
     ) -> Result<(), ChannelError> {
+        if self.review.holds(&msg.channel) {
+            self.review.hold_reply(msg, response).await;
+            return Ok(());
+        }
         let channels = self.channels.read().await;
-        if let Some(channel) = channels.get(&msg.channel) {
+        let result = if let Some(channel) = channels.get(&msg.channel) {
             channel.respond(msg, response).await
         } else {
             Err(ChannelError::SendFailed {
                 name: msg.channel.clone(),
                 reason: "Channel not found".to_string(),
             })
+        };
+        if result.is_err() {
+            crate::observability::metrics::record_channel_delivery_failure(&msg.channel, "respond");
         }
+        result
     }
 
     /// Send a status update to a specific channel.
@@ -162,7 +180,20 @@ impl ChannelManager {
     ) -> Result<(), ChannelError> {
         let channels = self.channels.read().await;
         if let Some(channel) = channels.get(channel_name) {
-            channel.broadcast(user_id, response).await
+            if self.review.holds(channel_name) {
+                self.review
+                    .hold_broadcast(channel_name, user_id, response)
+                    .await;
+                return Ok(());
+            }
+            let result = channel.broadcast(user_id, response).await;
+            if result.is_err() {
+                crate::observability::metrics::record_channel_delivery_failure(
+                    channel_name,
+                    "broadcast",
+                );
+            }
+            result
         } else {
             Err(ChannelError::SendFailed {
                 name: channel_name.to_string(),
@@ -183,7 +214,17 @@ impl ChannelManager {
         let mut results = Vec::new();
 
         for (name, channel) in channels.iter() {
-            let result = channel.broadcast(user_id, response.clone()).await;
+            let result = if self.review.holds(name) {
+                self.review
+                    .hold_broadcast(name, user_id, response.clone())
+                    .await;
+                Ok(())
+            } else {
+                channel.broadcast(user_id, response.clone()).await
+            };
+            if result.is_err() {
+                crate::observability::metrics::record_channel_delivery_failure(name, "broadcast");
+            }
             results.push((name.clone(), result));
         }
 
@@ -29,17 +29,26 @@
 
 mod channel;
 mod http;
+mod intake;
 mod manager;
 mod repl;
+pub mod review;
 mod signal;
+mod sms;
 pub mod wasm;
 pub mod web;
 mod webhook_server;
 
 pub use channel::{Channel, IncomingMessage, MessageStream, OutgoingResponse, StatusUpdate};
 pub use http::HttpChannel;
+pub use intake::{
+    DEFAULT_INTAKE_CAPACITY, DEFAULT_PER_USER_IN_FLIGHT, INTAKE_RETRY_AFTER, IntakeConfig,
+    IntakeError, IntakeQueue, IntakeStats,
+};
 pub use manager::ChannelManager;
 pub use repl::ReplChannel;
+pub use review::{DraftStatus, OutboundDraft, ReviewError, ReviewQueue};
 pub use signal::SignalChannel;
+pub use sms::SmsChannel;
 pub use web::GatewayChannel;
 pub use webhook_server::{WebhookServer, WebhookServerConfig};
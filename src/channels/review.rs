@@ -0,0 +1,659 @@
+//! Supervised-mode review queue for outbound communications.
+//!
+//! When supervised mode is on, replies and broadcasts the agent addresses to
+//! external channels (Telegram, Signal, HTTP webhooks, WASM channels, ...)
+//! are held as drafts instead of being sent. A reviewer approves a draft
+//! (optionally editing it first) to deliver it, or rejects it so it is never
+//! sent. The gateway and REPL are the operator's own interfaces and are never
+//! held.
+//!
+//! Drafts live in memory. A restart drops pending drafts unsent, which fails
+//! closed.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::channels::{Channel, IncomingMessage, OutgoingResponse};
+use crate::error::ChannelError;
+
+/// Channels that talk to the operator rather than to clients.
+pub const SUPERVISION_EXEMPT_CHANNELS: &[&str] = &["gateway", "repl"];
+
+/// Decided drafts kept for the review history.
+const MAX_DECIDED_DRAFTS: usize = 500;
+
+pub(crate) type ChannelMap = Arc<RwLock<HashMap<String, Box<dyn Channel>>>>;
+
+/// Review state of a held outbound draft.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DraftStatus {
+    Pending,
+    /// Approved and being delivered; becomes `Approved` once sent, or
+    /// `Pending` again if delivery fails.
+    Sending,
+    Approved,
+    Rejected,
+}
+
+impl DraftStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Sending => "sending",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+        }
+    }
+
+    fn is_decided(self) -> bool {
+        matches!(self, Self::Approved | Self::Rejected)
+    }
+}
+
+impl std::str::FromStr for DraftStatus {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "pending" => Ok(Self::Pending),
+            "sending" => Ok(Self::Sending),
+            "approved" => Ok(Self::Approved),
+            "rejected" => Ok(Self::Rejected),
+            other => Err(format!("unknown draft status '{other}'")),
+        }
+    }
+}
+
+/// How a draft is delivered once approved.
+#[derive(Debug, Clone)]
+enum DraftDelivery {
+    /// Reply to the message that produced it.
+    Reply(Box<IncomingMessage>),
+    /// Proactive message to a user (heartbeat alerts, notifications).
+    Broadcast { user_id: String },
+}
+
+/// An outbound communication awaiting or past review.
+#[derive(Debug, Clone)]
+pub struct OutboundDraft {
+    pub id: Uuid,
+    pub channel: String,
+    /// Recipient on the channel.
+    pub recipient: String,
+    pub thread_id: Option<String>,
+    /// Content as generated by the agent.
+    pub original_content: String,
+    /// Content that will be (or was) sent; differs from `original_content`
+    /// after an edit.
+    pub content: String,
+    pub status: DraftStatus,
+    pub created_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub decided_by: Option<String>,
+    pub rejection_reason: Option<String>,
+    metadata: serde_json::Value,
+    delivery: DraftDelivery,
+}
+
+impl OutboundDraft {
+    pub fn kind(&self) -> &'static str {
+        match self.delivery {
+            DraftDelivery::Reply(_) => "reply",
+            DraftDelivery::Broadcast { .. } => "broadcast",
+        }
+    }
+
+    pub fn edited(&self) -> bool {
+        self.content != self.original_content
+    }
+
+    fn response(&self) -> OutgoingResponse {
+        let mut response = OutgoingResponse::text(self.content.clone());
+        if let Some(ref thread_id) = self.thread_id {
+            response = response.in_thread(thread_id.clone());
+        }
+        response.metadata = self.metadata.clone();
+        response
+    }
+}
+
+/// Errors from review actions.
+#[derive(Debug, thiserror::Error)]
+pub enum ReviewError {
+    #[error("Draft {0} not found")]
+    NotFound(Uuid),
+
+    #[error("Draft {id} is already {}", .status.as_str())]
+    AlreadyDecided { id: Uuid, status: DraftStatus },
+
+    #[error("Draft content must not be empty")]
+    EmptyContent,
+
+    #[error(transparent)]
+    Delivery(#[from] ChannelError),
+}
+
+/// Holds outbound messages for review while supervised mode is on.
+pub struct ReviewQueue {
+    enabled: AtomicBool,
+    channels: ChannelMap,
+    drafts: Arc<RwLock<Vec<OutboundDraft>>>,
+}
+
+impl ReviewQueue {
+    pub(crate) fn new(channels: ChannelMap) -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            channels,
+            drafts: Arc::default(),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Turn supervised mode on or off. Turning it off does not release
+    /// drafts already held.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Whether a send on `channel` must be held for review.
+    pub fn holds(&self, channel: &str) -> bool {
+        self.enabled() && !SUPERVISION_EXEMPT_CHANNELS.contains(&channel)
+    }
+
+    /// Hold a reply to `msg` for review.
+    pub async fn hold_reply(&self, msg: &IncomingMessage, response: OutgoingResponse) -> Uuid {
+        let thread_id = response.thread_id.clone().or_else(|| msg.thread_id.clone());
+        self.push(
+            msg.channel.clone(),
+            msg.user_id.clone(),
+            thread_id,
+            response,
+            DraftDelivery::Reply(Box::new(msg.clone())),
+        )
+        .await
+    }
+
+    /// Hold a proactive message to `user_id` on `channel` for review.
+    pub async fn hold_broadcast(
+        &self,
+        channel: &str,
+        user_id: &str,
+        response: OutgoingResponse,
+    ) -> Uuid {
+        self.push(
+            channel.to_string(),
+            user_id.to_string(),
+            response.thread_id.clone(),
+            response,
+            DraftDelivery::Broadcast {
+                user_id: user_id.to_string(),
+            },
+        )
+        .await
+    }
+
+    async fn push(
+        &self,
+        channel: String,
+        recipient: String,
+        thread_id: Option<String>,
+        response: OutgoingResponse,
+        delivery: DraftDelivery,
+    ) -> Uuid {
+        let draft = OutboundDraft {
+            id: Uuid::new_v4(),
+            channel,
+            recipient,
+            thread_id,
+            original_content: response.content.clone(),
+            content: response.content,
+            status: DraftStatus::Pending,
+            created_at: Utc::now(),
+            decided_at: None,
+            decided_by: None,
+            rejection_reason: None,
+            metadata: response.metadata,
+            delivery,
+        };
+        let id = draft.id;
+        tracing::info!(
+            draft_id = %id,
+            channel = %draft.channel,
+            "Supervised mode: outbound message held for review"
+        );
+        let mut drafts = self.drafts.write().await;
+        drafts.push(draft);
+        prune_decided(&mut drafts);
+        id
+    }
+
+    /// Drafts, newest first, optionally filtered by status.
+    pub async fn list(&self, status: Option<DraftStatus>) -> Vec<OutboundDraft> {
+        self.drafts
+            .read()
+            .await
+            .iter()
+            .rev()
+            .filter(|d| status.is_none_or(|s| d.status == s))
+            .cloned()
+            .collect()
+    }
+
+    pub async fn get(&self, id: Uuid) -> Option<OutboundDraft> {
+        self.drafts
+            .read()
+            .await
+            .iter()
+            .find(|d| d.id == id)
+            .cloned()
+    }
+
+    pub async fn pending_count(&self) -> usize {
+        self.drafts
+            .read()
+            .await
+            .iter()
+            .filter(|d| d.status == DraftStatus::Pending)
+            .count()
+    }
+
+    /// Replace a pending draft's content without sending it.
+    pub async fn edit(&self, id: Uuid, content: String) -> Result<OutboundDraft, ReviewError> {
+        if content.trim().is_empty() {
+            return Err(ReviewError::EmptyContent);
+        }
+        let mut drafts = self.drafts.write().await;
+        let draft = pending_mut(&mut drafts, id)?;
+        draft.content = content;
+        Ok(draft.clone())
+    }
+
+    /// Deliver a pending draft, applying `content` as a final edit if given.
+    ///
+    /// The draft is claimed as `Sending` before delivery, so a second
+    /// reviewer cannot send it twice, and the queue lock is not held while
+    /// the channel sends. It returns to pending when delivery fails so it
+    /// can be retried. Delivery runs on its own task, so a caller that goes
+    /// away mid-send (a dropped HTTP request) cannot strand the draft in
+    /// `Sending`.
+    pub async fn approve(
+        &self,
+        id: Uuid,
+        reviewer: &str,
+        content: Option<String>,
+    ) -> Result<OutboundDraft, ReviewError> {
+        if content.as_deref().is_some_and(|c| c.trim().is_empty()) {
+            return Err(ReviewError::EmptyContent);
+        }
+        let mut outgoing = {
+            let mut drafts = self.drafts.write().await;
+            let draft = pending_mut(&mut drafts, id)?;
+            draft.status = DraftStatus::Sending;
+            draft.clone()
+        };
+        if let Some(content) = content {
+            outgoing.content = content;
+        }
+        let channels = Arc::clone(&self.channels);
+        let drafts = Arc::clone(&self.drafts);
+        let reviewer = reviewer.to_string();
+        let sending = tokio::spawn(async move {
+            let delivered = deliver(&channels, &outgoing).await;
+            let mut drafts = drafts.write().await;
+            // Sending drafts are never pruned, so the claimed draft is still here.
+            let draft = drafts
+                .iter_mut()
+                .find(|d| d.id == id)
+                .ok_or(ReviewError::NotFound(id))?;
+            if let Err(e) = delivered {
+                draft.status = DraftStatus::Pending;
+                return Err(e.into());
+            }
+            draft.content = outgoing.content;
+            draft.status = DraftStatus::Approved;
+            draft.decided_at = Some(Utc::now());
+            draft.decided_by = Some(reviewer);
+            Ok(draft.clone())
+        });
+        match sending.await {
+            Ok(result) => result,
+            Err(e) => {
+                // The channel panicked mid-send; release the claim.
+                if let Some(draft) = self.drafts.write().await.iter_mut().find(|d| d.id == id) {
+                    draft.status = DraftStatus::Pending;
+                }
+                Err(ReviewError::Delivery(ChannelError::SendFailed {
+                    name: "review".to_string(),
+                    reason: e.to_string(),
+                }))
+            }
+        }
+    }
+
+    /// Reject a pending draft; it is never sent.
+    pub async fn reject(
+        &self,
+        id: Uuid,
+        reviewer: &str,
+        reason: Option<String>,
+    ) -> Result<OutboundDraft, ReviewError> {
+        let mut drafts = self.drafts.write().await;
+        let draft = pending_mut(&mut drafts, id)?;
+        draft.status = DraftStatus::Rejected;
+        draft.decided_at = Some(Utc::now());
+        draft.decided_by = Some(reviewer.to_string());
+        draft.rejection_reason = reason.filter(|r| !r.trim().is_empty());
+        Ok(draft.clone())
+    }
+}
+
+async fn deliver(channels: &ChannelMap, draft: &OutboundDraft) -> Result<(), ChannelError> {
+    let channels = channels.read().await;
+    let channel = channels
+        .get(&draft.channel)
+        .ok_or_else(|| ChannelError::SendFailed {
+            name: draft.channel.clone(),
+            reason: "Channel not found".to_string(),
+        })?;
+    match &draft.delivery {
+        DraftDelivery::Reply(msg) => channel.respond(msg, draft.response()).await,
+        DraftDelivery::Broadcast { user_id } => channel.broadcast(user_id, draft.response()).await,
+    }
+}
+
+fn pending_mut(drafts: &mut [OutboundDraft], id: Uuid) -> Result<&mut OutboundDraft, ReviewError> {
+    let draft = drafts
+        .iter_mut()
+        .find(|d| d.id == id)
+        .ok_or(ReviewError::NotFound(id))?;
+    if draft.status != DraftStatus::Pending {
+        return Err(ReviewError::AlreadyDecided {
+            id,
+            status: draft.status,
+        });
+    }
+    Ok(draft)
+}
+
+/// Drop the oldest decided drafts beyond [`MAX_DECIDED_DRAFTS`]. Pending
+/// and sending drafts are never dropped.
+fn prune_decided(drafts: &mut Vec<OutboundDraft>) {
+    let decided = drafts.iter().filter(|d| d.status.is_decided()).count();
+    let mut excess = decided.saturating_sub(MAX_DECIDED_DRAFTS);
+    if excess > 0 {
+        drafts.retain(|d| {
+            if excess > 0 && d.status.is_decided() {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::channels::MessageStream;
+
+    #[derive(Default)]
+    struct RecordingChannel {
+        sent: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Channel for RecordingChannel {
+        fn name(&self) -> &str {
+            "telegram"
+        }
+
+        async fn start(&self) -> Result<MessageStream, ChannelError> {
+            Ok(Box::pin(futures::stream::empty()))
+        }
+
+        async fn respond(
+            &self,
+            _msg: &IncomingMessage,
+            response: OutgoingResponse,
+        ) -> Result<(), ChannelError> {
+            self.sent.lock().unwrap().push(response.content);
+            Ok(())
+        }
+
+        async fn broadcast(
+            &self,
+            user_id: &str,
+            response: OutgoingResponse,
+        ) -> Result<(), ChannelError> {
+            self.sent
+                .lock()
+                .unwrap()
+                .push(format!("{user_id}: {}", response.content));
+            Ok(())
+        }
+
+        async fn health_check(&self) -> Result<(), ChannelError> {
+            Ok(())
+        }
+    }
+
+    async fn queue_with_channel() -> (ReviewQueue, Arc<Mutex<Vec<String>>>) {
+        let channel = RecordingChannel::default();
+        let sent = Arc::clone(&channel.sent);
+        let channels: ChannelMap = Arc::default();
+        channels
+            .write()
+            .await
+            .insert("telegram".to_string(), Box::new(channel));
+        let queue = ReviewQueue::new(channels);
+        queue.set_enabled(true);
+        (queue, sent)
+    }
+
+    #[tokio::test]
+    async fn only_external_channels_are_held_in_supervised_mode() {
+        let (queue, _) = queue_with_channel().await;
+        assert!(queue.holds("telegram"));
+        assert!(!queue.holds("gateway"));
+        assert!(!queue.holds("repl"));
+        queue.set_enabled(false);
+        assert!(!queue.holds("telegram"));
+    }
+
+    #[tokio::test]
+    async fn approve_with_edit_delivers_edited_content_once() {
+        let (queue, sent) = queue_with_channel().await;
+        let msg = IncomingMessage::new("telegram", "client-1", "status?");
+        let id = queue
+            .hold_reply(&msg, OutgoingResponse::text("Draft reply"))
+            .await;
+        assert_eq!(queue.pending_count().await, 1);
+        assert!(sent.lock().unwrap().is_empty());
+
+        let approved = queue
+            .approve(id, "partner", Some("Reviewed reply".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(approved.status, DraftStatus::Approved);
+        assert!(approved.edited());
+        assert_eq!(*sent.lock().unwrap(), vec!["Reviewed reply"]);
+
+        assert!(matches!(
+            queue.approve(id, "partner", None).await,
+            Err(ReviewError::AlreadyDecided { .. })
+        ));
+        assert_eq!(sent.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn rejected_drafts_are_never_sent() {
+        let (queue, sent) = queue_with_channel().await;
+        let id = queue
+            .hold_broadcast("telegram", "client-1", OutgoingResponse::text("Alert"))
+            .await;
+        let rejected = queue
+            .reject(id, "partner", Some("Not for the client".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(rejected.kind(), "broadcast");
+        assert_eq!(
+            rejected.rejection_reason.as_deref(),
+            Some("Not for the client")
+        );
+        assert!(sent.lock().unwrap().is_empty());
+        assert!(queue.list(Some(DraftStatus::Pending)).await.is_empty());
+        assert_eq!(queue.list(None).await.len(), 1);
+    }
+
+    /// Blocks delivery until released, to observe the queue mid-send.
+    struct GatedChannel {
+        release: Arc<tokio::sync::Notify>,
+        sent: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Channel for GatedChannel {
+        fn name(&self) -> &str {
+            "telegram"
+        }
+
+        async fn start(&self) -> Result<MessageStream, ChannelError> {
+            Ok(Box::pin(futures::stream::empty()))
+        }
+
+        async fn respond(
+            &self,
+            _msg: &IncomingMessage,
+            _response: OutgoingResponse,
+        ) -> Result<(), ChannelError> {
+            Ok(())
+        }
+
+        async fn broadcast(
+            &self,
+            _user_id: &str,
+            response: OutgoingResponse,
+        ) -> Result<(), ChannelError> {
+            self.release.notified().await;
+            self.sent.lock().unwrap().push(response.content);
+            Ok(())
+        }
+
+        async fn health_check(&self) -> Result<(), ChannelError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn delivery_runs_outside_the_queue_lock_and_sends_once() {
+        let release = Arc::new(tokio::sync::Notify::new());
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let channels: ChannelMap = Arc::default();
+        channels.write().await.insert(
+            "telegram".to_string(),
+            Box::new(GatedChannel {
+                release: Arc::clone(&release),
+                sent: Arc::clone(&sent),
+            }),
+        );
+        let queue = Arc::new(ReviewQueue::new(channels));
+        queue.set_enabled(true);
+        let id = queue
+            .hold_broadcast("telegram", "client-1", OutgoingResponse::text("Alert"))
+            .await;
+
+        let approving = tokio::spawn({
+            let queue = Arc::clone(&queue);
+            async move { queue.approve(id, "partner", None).await }
+        });
+        // Readers are not blocked while the channel sends.
+        while queue.get(id).await.unwrap().status != DraftStatus::Sending {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(queue.pending_count().await, 0);
+        assert!(matches!(
+            queue.approve(id, "associate", None).await,
+            Err(ReviewError::AlreadyDecided {
+                status: DraftStatus::Sending,
+                ..
+            })
+        ));
+        assert!(queue.reject(id, "associate", None).await.is_err());
+
+        release.notify_one();
+        let approved = approving.await.unwrap().unwrap();
+        assert_eq!(approved.status, DraftStatus::Approved);
+        assert_eq!(approved.decided_by.as_deref(), Some("partner"));
+        assert_eq!(*sent.lock().unwrap(), vec!["Alert"]);
+    }
+
+    #[tokio::test]
+    async fn dropping_the_approve_call_mid_send_still_settles_the_draft() {
+        let release = Arc::new(tokio::sync::Notify::new());
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let channels: ChannelMap = Arc::default();
+        channels.write().await.insert(
+            "telegram".to_string(),
+            Box::new(GatedChannel {
+                release: Arc::clone(&release),
+                sent: Arc::clone(&sent),
+            }),
+        );
+        let queue = ReviewQueue::new(channels);
+        queue.set_enabled(true);
+        let id = queue
+            .hold_broadcast("telegram", "client-1", OutgoingResponse::text("Alert"))
+            .await;
+
+        // The caller gives up while the channel is still sending.
+        let timed_out = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            queue.approve(id, "partner", None),
+        )
+        .await;
+        assert!(timed_out.is_err());
+        assert_eq!(queue.get(id).await.unwrap().status, DraftStatus::Sending);
+
+        release.notify_one();
+        while queue.get(id).await.unwrap().status == DraftStatus::Sending {
+            tokio::task::yield_now().await;
+        }
+        let draft = queue.get(id).await.unwrap();
+        assert_eq!(draft.status, DraftStatus::Approved);
+        assert_eq!(draft.decided_by.as_deref(), Some("partner"));
+        assert_eq!(*sent.lock().unwrap(), vec!["Alert"]);
+    }
+
+    #[tokio::test]
+    async fn failed_delivery_keeps_draft_pending() {
+        let queue = ReviewQueue::new(Arc::default());
+        queue.set_enabled(true);
+        let id = queue
+            .hold_broadcast("signal", "client-1", OutgoingResponse::text("Hi"))
+            .await;
+        assert!(matches!(
+            queue.approve(id, "partner", None).await,
+            Err(ReviewError::Delivery(_))
+        ));
+        assert_eq!(queue.get(id).await.unwrap().status, DraftStatus::Pending);
+        assert!(matches!(
+            queue.edit(id, "  ".to_string()).await,
+            Err(ReviewError::EmptyContent)
+        ));
+    }
+}
@@ -3,6 +3,11 @@
 //! Connects to a running `signal-cli daemon --http <host:port>`.
 //! Listens for messages via SSE at `/api/v1/events` and sends via
 //! JSON-RPC at `/api/v1/rpc`.
+//!
+//! Signal messages are end-to-end encrypted between the client and the
+//! firm's signal-cli account. Groups listed in `matter_groups` map one group
+//! to one matter: messages and replies in that group are appended to the
+//! matter's `communications/contact_log.md`.
 
 use std::num::NonZeroUsize;
 use std::sync::Arc;
@@ -10,6 +15,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use async_trait::async_trait;
+use chrono::Utc;
 use futures::StreamExt;
 use lru::LruCache;
 use reqwest::Client;
@@ -20,7 +26,9 @@ use uuid::Uuid;
 use crate::channels::{Channel, IncomingMessage, MessageStream, OutgoingResponse, StatusUpdate};
 use crate::config::SignalConfig;
 use crate::error::ChannelError;
+use crate::legal::correspondence::{CONTACT_LOG_TEMPLATE, channel_message_row, contact_log_path};
 use crate::pairing::PairingStore;
+use crate::workspace::Workspace;
 
 const GROUP_TARGET_PREFIX: &str = "group:";
 const SIGNAL_HEALTH_ENDPOINT: &str = "/api/v1/check";
@@ -84,6 +92,33 @@ struct GroupInfo {
     group_id: Option<String>,
 }
 
+/// Appends messages from matter-mapped groups to matter contact logs.
+struct MatterLog {
+    workspace: Arc<Workspace>,
+    matter_root: String,
+    /// Serializes read-modify-write appends so concurrent messages are not lost.
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+impl MatterLog {
+    async fn append(&self, matter_id: &str, with: &str, inbound: bool, text: &str) {
+        let path = contact_log_path(&self.matter_root, matter_id);
+        let _guard = self.write_lock.lock().await;
+        let existing = match self.workspace.read(&path).await {
+            Ok(doc) if !doc.content.trim().is_empty() => doc.content,
+            _ => CONTACT_LOG_TEMPLATE.to_string(),
+        };
+        let row = channel_message_row(Utc::now(), with, "Signal", inbound, text);
+        let updated = format!("{}\n{}", existing.trim_end(), row);
+        if let Err(e) = self.workspace.write(&path, &updated).await {
+            tracing::warn!(
+                matter_id = %matter_id,
+                "Signal: failed to append to matter contact log: {e}"
+            );
+        }
+    }
+}
+
 /// Signal channel using signal-cli daemon's native JSON-RPC + SSE API.
 pub struct SignalChannel {
     config: SignalConfig,
@@ -94,6 +129,8 @@ pub struct SignalChannel {
     reply_targets: Arc<RwLock<LruCache<Uuid, String>>>,
     /// Debug mode for verbose tool output (toggled via /debug command).
     debug_mode: Arc<AtomicBool>,
+    /// Contact log writer for matter-mapped groups (set via `with_matter_log`).
+    matter_log: Option<Arc<MatterLog>>,
 }
 
 impl SignalChannel {
@@ -111,7 +148,24 @@ impl SignalChannel {
         let reply_targets = Arc::new(RwLock::new(LruCache::new(cap)));
         let debug_mode = Arc::new(AtomicBool::new(false));
 
-        Ok(Self::from_parts(config, client, reply_targets, debug_mode))
+        Ok(Self::from_parts(
+            config,
+            client,
+            reply_targets,
+            debug_mode,
+            None,
+        ))
+    }
+
+    /// Record messages in groups listed in `matter_groups` in each matter's
+    /// contact log under `matter_root` in the workspace.
+    pub fn with_matter_log(mut self, workspace: Arc<Workspace>, matter_root: &str) -> Self {
+        self.matter_log = Some(Arc::new(MatterLog {
+            workspace,
+            matter_root: matter_root.to_string(),
+            write_lock: tokio::sync::Mutex::new(()),
+        }));
+        self
     }
 
     /// Construct a SignalChannel from pre-validated parts.
@@ -123,15 +177,40 @@ impl SignalChannel {
         client: Client,
         reply_targets: Arc<RwLock<LruCache<Uuid, String>>>,
         debug_mode: Arc<AtomicBool>,
+        matter_log: Option<Arc<MatterLog>>,
     ) -> Self {
         Self {
             config,
             client,
             reply_targets,
             debug_mode,
+            matter_log,
         }
     }
 
+    /// Matter mapped to the group a message came from, if any.
+    fn matter_for_group(&self, data_msg: &DataMessage) -> Option<&str> {
+        let group_id = data_msg.group_info.as_ref()?.group_id.as_deref()?;
+        self.config.matter_groups.get(group_id).map(String::as_str)
+    }
+
+    /// Append a message to the contact log of the matter its group maps to.
+    async fn log_to_matter(&self, msg: &IncomingMessage, inbound: bool, text: &str) {
+        let (Some(log), Some(matter_id)) = (
+            self.matter_log.as_ref(),
+            msg.metadata
+                .get("signal_matter_id")
+                .and_then(|v| v.as_str()),
+        ) else {
+            return;
+        };
+        let with = match msg.user_name.as_deref() {
+            Some(name) => format!("{name} ({})", msg.user_id),
+            None => msg.user_id.clone(),
+        };
+        log.append(matter_id, &with, inbound, text).await;
+    }
+
     fn is_debug(&self) -> bool {
         self.debug_mode.load(Ordering::Relaxed)
     }
@@ -710,6 +789,7 @@ impl SignalChannel {
             "signal_sender": &sender,
             "signal_target": &target,
             "signal_timestamp": timestamp,
+            "signal_matter_id": self.matter_for_group(data_msg),
         });
 
         let mut msg = IncomingMessage::new("signal", &sender, text).with_metadata(metadata);
@@ -753,9 +833,12 @@ impl Channel for SignalChannel {
         let client = self.client.clone();
         let reply_targets = Arc::clone(&self.reply_targets);
         let debug_mode = Arc::clone(&self.debug_mode);
+        let matter_log = self.matter_log.clone();
 
         tokio::spawn(async move {
-            if let Err(e) = sse_listener(config, client, tx, reply_targets, debug_mode).await {
+            if let Err(e) =
+                sse_listener(config, client, tx, reply_targets, debug_mode, matter_log).await
+            {
                 tracing::error!("Signal SSE listener exited with error: {e}");
             }
         });
@@ -792,6 +875,7 @@ impl Channel for SignalChannel {
         let target = Self::parse_recipient_target(&target_str);
         let params = self.build_rpc_params(&target, Some(&response.content));
         self.rpc_request("send", params).await?;
+        self.log_to_matter(msg, false, &response.content).await;
 
         // Clean up stored target.
         self.reply_targets.write().await.pop(&msg.id);
@@ -1001,12 +1085,14 @@ async fn sse_listener(
     tx: tokio::sync::mpsc::Sender<IncomingMessage>,
     reply_targets: Arc<RwLock<LruCache<Uuid, String>>>,
     debug_mode: Arc<AtomicBool>,
+    matter_log: Option<Arc<MatterLog>>,
 ) -> Result<(), ChannelError> {
     let channel = SignalChannel::from_parts(
         config,
         client,
         Arc::clone(&reply_targets),
         Arc::clone(&debug_mode),
+        matter_log,
     );
 
     let mut url = reqwest::Url::parse(&format!("{}/api/v1/events", channel.config.http_url))
@@ -1200,6 +1286,7 @@ async fn sse_listener(
                                         let mut targets = reply_targets.write().await;
                                         targets.put(msg.id, target);
                                     }
+                                    channel.log_to_matter(&msg, true, &msg.content).await;
                                     if tx.send(msg).await.is_err() {
                                         tracing::debug!("Signal SSE: receiver dropped, exiting");
                                         return Ok(());
@@ -1234,6 +1321,7 @@ async fn sse_listener(
             && let Some((msg, target)) = channel.process_envelope(envelope)
         {
             reply_targets.write().await.put(msg.id, target);
+            channel.log_to_matter(&msg, true, &msg.content).await;
             let _ = tx.send(msg).await;
         }
 
@@ -1258,6 +1346,7 @@ mod tests {
             group_allow_from: vec![],
             ignore_attachments: false,
             ignore_stories: false,
+            matter_groups: std::collections::HashMap::new(),
         }
     }
 
@@ -1273,6 +1362,7 @@ mod tests {
             group_allow_from: vec![],
             ignore_attachments: true,
             ignore_stories: true,
+            matter_groups: std::collections::HashMap::new(),
         }
     }
 
@@ -1535,6 +1625,79 @@ mod tests {
         Ok(())
     }
 
+    fn make_group_envelope(group_id: &str, message: &str) -> Envelope {
+        Envelope {
+            source: Some("+1111111111".to_string()),
+            source_number: Some("+1111111111".to_string()),
+            source_name: Some("Jane Client".to_string()),
+            source_uuid: None,
+            data_message: Some(DataMessage {
+                message: Some(message.to_string()),
+                timestamp: Some(1000),
+                group_info: Some(GroupInfo {
+                    group_id: Some(group_id.to_string()),
+                }),
+                attachments: None,
+            }),
+            story_message: None,
+            timestamp: Some(1000),
+        }
+    }
+
+    #[test]
+    fn process_envelope_tags_matter_groups() -> Result<(), ChannelError> {
+        let mut config = make_config_with_allowed_group("*");
+        config
+            .matter_groups
+            .insert("grpAcme==".to_string(), "acme-v-doe".to_string());
+        let ch = SignalChannel::new(config)?;
+
+        let (msg, _) = ch
+            .process_envelope(&make_group_envelope("grpAcme==", "hi"))
+            .unwrap();
+        assert_eq!(msg.metadata["signal_matter_id"], "acme-v-doe");
+
+        let (msg, _) = ch
+            .process_envelope(&make_group_envelope("grpOther", "hi"))
+            .unwrap();
+        assert!(msg.metadata["signal_matter_id"].is_null());
+        Ok(())
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn matter_group_messages_land_in_contact_log() -> Result<(), ChannelError> {
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", db));
+        let mut config = make_config_with_allowed_group("*");
+        config
+            .matter_groups
+            .insert("grpAcme==".to_string(), "acme-v-doe".to_string());
+        let ch = SignalChannel::new(config)?.with_matter_log(Arc::clone(&workspace), "matters");
+
+        let (msg, _) = ch
+            .process_envelope(&make_group_envelope("grpAcme==", "Did the court rule?"))
+            .unwrap();
+        ch.log_to_matter(&msg, true, &msg.content).await;
+        ch.log_to_matter(&msg, false, "Not yet; expected Friday.")
+            .await;
+
+        let log = workspace
+            .read("matters/acme-v-doe/communications/contact_log.md")
+            .await
+            .unwrap()
+            .content;
+        assert!(log.starts_with(CONTACT_LOG_TEMPLATE.trim_end()));
+        assert!(
+            log.contains(
+                "| Jane Client (+1111111111) | Signal | Received \"Did the court rule?\" |"
+            )
+        );
+        assert!(log.contains("| Signal | Sent \"Not yet; expected Friday.\" |"));
+
+        Ok(())
+    }
+
     #[test]
     fn reply_target_dm() {
         let dm = DataMessage {
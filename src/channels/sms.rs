@@ -0,0 +1,798 @@
+//! SMS channel via Twilio Programmable Messaging.
+//!
+//! Twilio posts inbound texts to `/sms/twilio` on the webhook server. Every
+//! request is checked against the `X-Twilio-Signature` header before it is
+//! read. Replies and proactive messages are sent through the Twilio REST API.
+//!
+//! Opt-out keywords (`STOP` and synonyms) and opt-in keywords (`START`,
+//! `YES`, `UNSTOP`) are answered by the channel itself and recorded as
+//! consent records in the database. Nothing is sent to an opted-out number.
+//! Proactive messages such as deadline reminders additionally need an
+//! opt-in on record, and end with an opt-out notice. Messages to a known
+//! client are also cleared against the client's communication preferences
+//! (see [`crate::legal::client_comms`]), and keywords are copied into the
+//! client's consent history. Texts from a client carry their preferred
+//! language (`sms_client_language`) so the agent replies in it.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    Form, Router,
+    extract::{DefaultBodyLimit, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::post,
+};
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use secrecy::ExposeSecret;
+use sha1::Sha1;
+use subtle::ConstantTimeEq;
+use tokio::sync::{RwLock, mpsc};
+
+use crate::channels::{
+    Channel, INTAKE_RETRY_AFTER, IncomingMessage, IntakeQueue, MessageStream, OutgoingResponse,
+};
+use crate::config::SmsConfig;
+use crate::db::{
+    ClientRecord, CommunicationChannel, CommunicationConsentStatus,
+    CreateClientCommunicationConsentParams, Database, SetSmsConsentParams, SmsConsentStatus,
+};
+use crate::error::ChannelError;
+use crate::legal::client_comms::{SendClearance, clear_client_send};
+
+type HmacSha1 = Hmac<Sha1>;
+
+const TWILIO_API_BASE: &str = "https://api.twilio.com/2010-04-01";
+const WEBHOOK_PATH: &str = "/sms/twilio";
+const SIGNATURE_HEADER: &str = "x-twilio-signature";
+
+/// Maximum form body size for webhook requests (64 KB).
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// Twilio rejects message bodies longer than this many characters.
+const MAX_SMS_CHARS: usize = 1600;
+
+/// Appended to proactive messages.
+const OPT_OUT_NOTICE: &str = "Reply STOP to opt out.";
+
+const OPT_OUT_KEYWORDS: &[&str] = &[
+    "STOP",
+    "STOPALL",
+    "UNSUBSCRIBE",
+    "CANCEL",
+    "END",
+    "QUIT",
+    "REVOKE",
+    "OPTOUT",
+];
+const OPT_IN_KEYWORDS: &[&str] = &["START", "YES", "UNSTOP", "OPTIN"];
+const HELP_KEYWORDS: &[&str] = &["HELP", "INFO"];
+
+const OPT_OUT_REPLY: &str =
+    "You are unsubscribed and will receive no further messages. Reply START to resubscribe.";
+const OPT_IN_REPLY: &str = "You are subscribed to messages about your matters, including reminders. Reply STOP to opt out.";
+const HELP_REPLY: &str = "Messages about your matters from your legal team. Reply STOP to opt out or START to resubscribe. Msg & data rates may apply.";
+
+/// Compliance keyword sent as the whole message body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Keyword {
+    OptOut,
+    OptIn,
+    Help,
+}
+
+impl Keyword {
+    fn parse(body: &str) -> Option<Self> {
+        let word = body
+            .trim()
+            .trim_end_matches(['.', '!'])
+            .to_ascii_uppercase();
+        if OPT_OUT_KEYWORDS.contains(&word.as_str()) {
+            Some(Self::OptOut)
+        } else if OPT_IN_KEYWORDS.contains(&word.as_str()) {
+            Some(Self::OptIn)
+        } else if HELP_KEYWORDS.contains(&word.as_str()) {
+            Some(Self::Help)
+        } else {
+            None
+        }
+    }
+}
+
+/// Twilio SMS channel.
+pub struct SmsChannel {
+    state: Arc<SmsChannelState>,
+}
+
+struct SmsChannelState {
+    config: SmsConfig,
+    store: Arc<dyn Database>,
+    client: Client,
+    /// Sender for incoming messages.
+    tx: RwLock<Option<mpsc::Sender<IncomingMessage>>>,
+    /// Admission control in front of `tx`.
+    intake: Arc<IntakeQueue>,
+}
+
+impl SmsChannel {
+    /// Create a new SMS channel. Consent records live in `store`.
+    pub fn new(config: SmsConfig, store: Arc<dyn Database>) -> Result<Self, ChannelError> {
+        let client = Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .map_err(|e| ChannelError::StartupFailed {
+                name: "sms".to_string(),
+                reason: format!("Failed to build HTTP client: {e}"),
+            })?;
+        Ok(Self {
+            state: Arc::new(SmsChannelState {
+                config,
+                store,
+                client,
+                tx: RwLock::new(None),
+                intake: Arc::new(IntakeQueue::default()),
+            }),
+        })
+    }
+
+    /// Return the channel's axum routes with state applied.
+    pub fn routes(&self) -> Router {
+        Router::new()
+            .route(WEBHOOK_PATH, post(webhook_handler))
+            .layer(DefaultBodyLimit::max(MAX_BODY_BYTES))
+            .with_state(self.state.clone())
+    }
+}
+
+impl SmsChannelState {
+    /// Client whose phone number matches `phone`, if any.
+    async fn client_for(&self, phone: &str) -> Option<ClientRecord> {
+        match self.store.list_clients(&self.config.owner_id, None).await {
+            Ok(clients) => clients.into_iter().find(|client| {
+                client
+                    .phone
+                    .as_deref()
+                    .is_some_and(|raw| phones_match(phone, raw))
+            }),
+            Err(e) => {
+                tracing::warn!("SMS: failed to look up clients: {e}");
+                None
+            }
+        }
+    }
+
+    /// The client's preferred language, so the agent replies in it.
+    async fn client_language(&self, client: &ClientRecord) -> Option<String> {
+        match self
+            .store
+            .get_client_communication_preferences(&self.config.owner_id, client.id)
+            .await
+        {
+            Ok(preferences) => preferences.and_then(|prefs| prefs.language),
+            Err(e) => {
+                tracing::warn!("SMS: failed to read communication preferences: {e}");
+                None
+            }
+        }
+    }
+
+    fn allow_listed(&self, phone: &str) -> bool {
+        self.config
+            .allow_from
+            .iter()
+            .any(|entry| entry == "*" || entry == phone)
+    }
+
+    async fn consent(&self, phone: &str) -> Result<Option<SmsConsentStatus>, ChannelError> {
+        self.store
+            .get_sms_consent(&self.config.owner_id, phone)
+            .await
+            .map(|record| record.map(|r| r.status))
+            .map_err(|e| send_failed(format!("failed to read SMS consent: {e}")))
+    }
+
+    async fn record_consent(
+        &self,
+        phone: &str,
+        status: SmsConsentStatus,
+        keyword: &str,
+    ) -> Result<(), ChannelError> {
+        let client_id = self.client_for(phone).await.map(|client| client.id);
+        self.store
+            .set_sms_consent(
+                &self.config.owner_id,
+                &SetSmsConsentParams {
+                    phone_number: phone.to_string(),
+                    client_id,
+                    status,
+                    source: keyword.to_string(),
+                },
+            )
+            .await
+            .map_err(|e| send_failed(format!("failed to record SMS consent: {e}")))?;
+        // Mirror the decision into the client's consent history.
+        let Some(client_id) = client_id else {
+            return Ok(());
+        };
+        self.store
+            .create_client_communication_consent(
+                &self.config.owner_id,
+                client_id,
+                &CreateClientCommunicationConsentParams {
+                    channel: CommunicationChannel::Sms,
+                    status: match status {
+                        SmsConsentStatus::OptedIn => CommunicationConsentStatus::Granted,
+                        SmsConsentStatus::OptedOut => CommunicationConsentStatus::Revoked,
+                    },
+                    source: format!("sms:{keyword}"),
+                    consented_at: chrono::Utc::now(),
+                    recorded_by: "sms".to_string(),
+                },
+            )
+            .await
+            .map(|_| ())
+            .map_err(|e| send_failed(format!("failed to record client consent: {e}")))
+    }
+
+    /// Clear a message to `phone` against the matching client's
+    /// communication preferences. Numbers that match no client pass.
+    async fn clear_for_client(
+        &self,
+        phone: &str,
+        client_initiated: bool,
+    ) -> Result<SendClearance, ChannelError> {
+        let Some(client) = self.client_for(phone).await else {
+            return Ok(SendClearance::default());
+        };
+        clear_client_send(
+            self.store.as_ref(),
+            &self.config.owner_id,
+            client.id,
+            CommunicationChannel::Sms,
+            chrono::Utc::now(),
+            client_initiated,
+        )
+        .await
+        .map_err(|block| send_failed(format!("message to {phone} held: {}", block.reason)))
+    }
+
+    async fn send_sms(&self, to: &str, body: &str) -> Result<(), ChannelError> {
+        let body: String = body.chars().take(MAX_SMS_CHARS).collect();
+        let url = format!(
+            "{}/Accounts/{}/Messages.json",
+            TWILIO_API_BASE, self.config.account_sid
+        );
+        let resp = self
+            .client
+            .post(&url)
+            .basic_auth(
+                &self.config.account_sid,
+                Some(self.config.auth_token.expose_secret()),
+            )
+            .form(&[
+                ("To", to),
+                ("From", self.config.from_number.as_str()),
+                ("Body", body.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|e| send_failed(format!("Twilio request failed: {e}")))?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let detail = resp
+                .json::<serde_json::Value>()
+                .await
+                .ok()
+                .and_then(|v| v.get("message").and_then(|m| m.as_str()).map(String::from))
+                .unwrap_or_default();
+            return Err(send_failed(format!("Twilio returned {status}: {detail}")));
+        }
+        Ok(())
+    }
+}
+
+fn send_failed(reason: String) -> ChannelError {
+    ChannelError::SendFailed {
+        name: "sms".to_string(),
+        reason,
+    }
+}
+
+/// Digits of a phone number, ignoring formatting.
+fn phone_digits(raw: &str) -> String {
+    raw.chars().filter(char::is_ascii_digit).collect()
+}
+
+/// True if the free-form phone number `raw` (from a client record) refers to
+/// the E.164 number `e164`. A number without a country code matches the
+/// trailing digits of the E.164 form.
+fn phones_match(e164: &str, raw: &str) -> bool {
+    let full = phone_digits(e164);
+    let other = phone_digits(raw);
+    !other.is_empty() && (full == other || (other.len() >= 10 && full.ends_with(&other)))
+}
+
+fn is_e164(value: &str) -> bool {
+    value
+        .strip_prefix('+')
+        .is_some_and(|digits| (8..=15).contains(&digits.len()) && phone_digits(digits) == digits)
+}
+
+/// Twilio's request signature: base64 HMAC-SHA1 over the webhook URL followed
+/// by each POST parameter name and value, sorted by name.
+fn twilio_signature(auth_token: &str, url: &str, params: &BTreeMap<String, String>) -> String {
+    let mut mac =
+        HmacSha1::new_from_slice(auth_token.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(url.as_bytes());
+    for (key, value) in params {
+        mac.update(key.as_bytes());
+        mac.update(value.as_bytes());
+    }
+    base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+}
+
+fn xml_escape(raw: &str) -> String {
+    raw.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// TwiML response, optionally replying with `message`.
+fn twiml(message: Option<&str>) -> Response {
+    let body = match message {
+        Some(text) => format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><Response><Message>{}</Message></Response>",
+            xml_escape(text)
+        ),
+        None => "<?xml version=\"1.0\" encoding=\"UTF-8\"?><Response/>".to_string(),
+    };
+    ([(header::CONTENT_TYPE, "application/xml")], body).into_response()
+}
+
+async fn webhook_handler(
+    State(state): State<Arc<SmsChannelState>>,
+    headers: HeaderMap,
+    Form(params): Form<BTreeMap<String, String>>,
+) -> Response {
+    let provided = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let expected = twilio_signature(
+        state.config.auth_token.expose_secret(),
+        &state.config.webhook_url,
+        &params,
+    );
+    if provided.is_empty() || !bool::from(expected.as_bytes().ct_eq(provided.as_bytes())) {
+        tracing::warn!("SMS webhook: rejected request with invalid Twilio signature");
+        return (StatusCode::FORBIDDEN, "Invalid signature").into_response();
+    }
+
+    let Some(from) = params.get("From").map(|s| s.trim().to_string()) else {
+        return (StatusCode::BAD_REQUEST, "Missing From").into_response();
+    };
+    let body = params.get("Body").cloned().unwrap_or_default();
+
+    if let Some(keyword) = Keyword::parse(&body) {
+        let (status, reply) = match keyword {
+            Keyword::OptOut => (Some(SmsConsentStatus::OptedOut), OPT_OUT_REPLY),
+            Keyword::OptIn => (Some(SmsConsentStatus::OptedIn), OPT_IN_REPLY),
+            Keyword::Help => (None, HELP_REPLY),
+        };
+        if let Some(status) = status {
+            let keyword = body.trim().to_ascii_uppercase();
+            if let Err(e) = state.record_consent(&from, status, &keyword).await {
+                tracing::error!("SMS webhook: {e}");
+                return (StatusCode::INTERNAL_SERVER_ERROR, "Consent not recorded").into_response();
+            }
+            tracing::info!(status = status.as_str(), "SMS consent recorded");
+        }
+        return twiml(Some(reply));
+    }
+
+    let client = state.client_for(&from).await;
+    if client.is_none() && !state.allow_listed(&from) {
+        tracing::debug!("SMS webhook: ignoring message from unknown number");
+        return twiml(None);
+    }
+    if body.trim().is_empty() {
+        return twiml(None);
+    }
+
+    let client_language = match client.as_ref() {
+        Some(client) => state.client_language(client).await,
+        None => None,
+    };
+    let metadata = serde_json::json!({
+        "sms_from": &from,
+        "sms_message_sid": params.get("MessageSid"),
+        "sms_client_id": client.as_ref().map(|c| c.id.to_string()),
+        "sms_client_language": client_language,
+    });
+    let mut msg = IncomingMessage::new("sms", &from, body).with_metadata(metadata);
+    if let Some(client) = client {
+        msg = msg.with_user_name(client.name);
+    }
+
+    let tx = state.tx.read().await.as_ref().cloned();
+    if let Err(err) = state.intake.submit(tx.as_ref(), msg) {
+        tracing::warn!("SMS webhook: message not queued: {err}");
+        let mut response = (StatusCode::SERVICE_UNAVAILABLE, err.to_string()).into_response();
+        if err.retry_after().is_some() {
+            *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+            response.headers_mut().insert(
+                header::RETRY_AFTER,
+                HeaderValue::from(INTAKE_RETRY_AFTER.as_secs()),
+            );
+        }
+        return response;
+    }
+    twiml(None)
+}
+
+#[async_trait]
+impl Channel for SmsChannel {
+    fn name(&self) -> &str {
+        "sms"
+    }
+
+    async fn start(&self) -> Result<MessageStream, ChannelError> {
+        let (tx, stream) = self.state.intake.channel();
+        *self.state.tx.write().await = Some(tx);
+        tracing::info!(path = WEBHOOK_PATH, "SMS channel ready");
+        Ok(stream)
+    }
+
+    async fn respond(
+        &self,
+        msg: &IncomingMessage,
+        response: OutgoingResponse,
+    ) -> Result<(), ChannelError> {
+        let to = msg
+            .metadata
+            .get("sms_from")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&msg.user_id);
+        if self.state.consent(to).await? == Some(SmsConsentStatus::OptedOut) {
+            return Err(send_failed(format!("{to} has opted out of SMS")));
+        }
+        let clearance = self.state.clear_for_client(to, true).await?;
+        self.state
+            .send_sms(to, &clearance.apply(&response.content))
+            .await
+    }
+
+    async fn broadcast(
+        &self,
+        user_id: &str,
+        response: OutgoingResponse,
+    ) -> Result<(), ChannelError> {
+        // Broadcasts addressed to non-phone users are meant for other channels.
+        if !is_e164(user_id) {
+            return Ok(());
+        }
+        if self.state.consent(user_id).await? != Some(SmsConsentStatus::OptedIn) {
+            return Err(send_failed(format!(
+                "{user_id} has no SMS opt-in on record"
+            )));
+        }
+        // Routine notifications carry the bare routine output in `summary`;
+        // the decorated `content` is meant for the operator.
+        let text = response
+            .metadata
+            .get("summary")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&response.content);
+        let clearance = self.state.clear_for_client(user_id, false).await?;
+        let body = format!("{}\n\n{OPT_OUT_NOTICE}", clearance.apply(text));
+        self.state.send_sms(user_id, &body).await
+    }
+
+    async fn health_check(&self) -> Result<(), ChannelError> {
+        if self.state.tx.read().await.is_some() {
+            Ok(())
+        } else {
+            Err(ChannelError::HealthCheckFailed {
+                name: "sms".to_string(),
+            })
+        }
+    }
+
+    async fn shutdown(&self) -> Result<(), ChannelError> {
+        *self.state.tx.write().await = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keywords_are_whole_message_and_case_insensitive() {
+        assert_eq!(Keyword::parse(" stop "), Some(Keyword::OptOut));
+        assert_eq!(Keyword::parse("Unsubscribe."), Some(Keyword::OptOut));
+        assert_eq!(Keyword::parse("start"), Some(Keyword::OptIn));
+        assert_eq!(Keyword::parse("HELP"), Some(Keyword::Help));
+        assert_eq!(Keyword::parse("please stop calling"), None);
+        assert_eq!(Keyword::parse(""), None);
+    }
+
+    #[test]
+    fn client_phones_match_e164() {
+        assert!(phones_match("+15551234567", "(555) 123-4567"));
+        assert!(phones_match("+15551234567", "+1 555 123 4567"));
+        assert!(!phones_match("+15551234567", "123-4567"));
+        assert!(!phones_match("+15551234567", ""));
+        assert!(is_e164("+15551234567"));
+        assert!(!is_e164("default"));
+        assert!(!is_e164("+1555-123"));
+    }
+
+    #[test]
+    fn signature_matches_twilio_reference() {
+        // Reference example from Twilio's request validator test suite.
+        let params: BTreeMap<String, String> = [
+            ("CallSid", "CA1234567890ABCDE"),
+            ("Caller", "+12349013030"),
+            ("Digits", "1234"),
+            ("From", "+12349013030"),
+            ("To", "+18005551212"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(
+            twilio_signature(
+                "12345",
+                "https://mycompany.com/myapp.php?foo=1&bar=2",
+                &params
+            ),
+            "0/KCTR6DLpKmkAf8muzZqo1nDgQ="
+        );
+    }
+
+    #[cfg(feature = "libsql")]
+    mod webhook {
+        use axum::body::Body;
+        use axum::http::Request;
+        use secrecy::SecretString;
+        use tower::ServiceExt;
+
+        use super::*;
+
+        const URL: &str = "https://firm.example/sms/twilio";
+        const TOKEN: &str = "auth-token";
+
+        fn config() -> SmsConfig {
+            SmsConfig {
+                account_sid: "AC123".to_string(),
+                auth_token: SecretString::from(TOKEN.to_string()),
+                from_number: "+15550000000".to_string(),
+                webhook_url: URL.to_string(),
+                owner_id: "default".to_string(),
+                allow_from: vec![],
+            }
+        }
+
+        fn request(params: &[(&str, &str)], signed: bool) -> Request<Body> {
+            let map: BTreeMap<String, String> = params
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            let body = url::form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&map)
+                .finish();
+            let signature = if signed {
+                twilio_signature(TOKEN, URL, &map)
+            } else {
+                "bogus".to_string()
+            };
+            Request::builder()
+                .method("POST")
+                .uri(WEBHOOK_PATH)
+                .header("content-type", "application/x-www-form-urlencoded")
+                .header(SIGNATURE_HEADER, signature)
+                .body(Body::from(body))
+                .unwrap()
+        }
+
+        async fn body_text(resp: Response) -> String {
+            let bytes = axum::body::to_bytes(resp.into_body(), 1024 * 1024)
+                .await
+                .unwrap();
+            String::from_utf8(bytes.to_vec()).unwrap()
+        }
+
+        #[tokio::test]
+        async fn unsigned_requests_are_rejected() {
+            let (db, _dir) = crate::testing::test_db().await;
+            let channel = SmsChannel::new(config(), db).unwrap();
+            let resp = channel
+                .routes()
+                .oneshot(request(&[("From", "+15551234567"), ("Body", "hi")], false))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        }
+
+        #[tokio::test]
+        async fn keywords_record_consent_and_gate_broadcasts() {
+            let (db, _dir) = crate::testing::test_db().await;
+            let channel = SmsChannel::new(config(), Arc::clone(&db)).unwrap();
+            let phone = "+15551234567";
+
+            // No opt-in yet: proactive messages are refused.
+            let err = channel
+                .broadcast(phone, OutgoingResponse::text("Reminder"))
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("no SMS opt-in"));
+            // Broadcasts to non-phone users are not for this channel.
+            channel
+                .broadcast("default", OutgoingResponse::text("Reminder"))
+                .await
+                .unwrap();
+
+            let resp = channel
+                .routes()
+                .oneshot(request(&[("From", phone), ("Body", "START")], true))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert!(body_text(resp).await.contains("You are subscribed"));
+            let record = db.get_sms_consent("default", phone).await.unwrap().unwrap();
+            assert_eq!(record.status, SmsConsentStatus::OptedIn);
+            assert_eq!(record.source, "START");
+
+            channel
+                .routes()
+                .oneshot(request(&[("From", phone), ("Body", "stop")], true))
+                .await
+                .unwrap();
+            let record = db.get_sms_consent("default", phone).await.unwrap().unwrap();
+            assert_eq!(record.status, SmsConsentStatus::OptedOut);
+
+            // Opted out: neither replies nor broadcasts go out.
+            let msg = IncomingMessage::new("sms", phone, "hello");
+            let err = channel
+                .respond(&msg, OutgoingResponse::text("hi"))
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("opted out"));
+        }
+
+        #[tokio::test]
+        async fn messages_from_clients_reach_the_agent() {
+            let (db, _dir) = crate::testing::test_db().await;
+            let client = db
+                .create_client(
+                    "default",
+                    &crate::db::CreateClientParams {
+                        name: "Jane Client".to_string(),
+                        client_type: crate::db::ClientType::Individual,
+                        email: None,
+                        phone: Some("(555) 123-4567".to_string()),
+                        address: None,
+                        notes: None,
+                    },
+                )
+                .await
+                .unwrap();
+            db.upsert_client_communication_preferences(
+                "default",
+                client.id,
+                &crate::db::UpsertClientCommunicationPreferencesParams {
+                    allowed_channels: vec![CommunicationChannel::Sms],
+                    quiet_hours: Vec::new(),
+                    timezone: None,
+                    language: Some("es".to_string()),
+                    disclaimers: Vec::new(),
+                    updated_by: "default".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+            let channel = SmsChannel::new(config(), db).unwrap();
+            let mut stream = channel.start().await.unwrap();
+
+            let resp = channel
+                .routes()
+                .oneshot(request(
+                    &[
+                        ("From", "+19998887777"),
+                        ("Body", "who is this"),
+                        ("MessageSid", "SM0"),
+                    ],
+                    true,
+                ))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+
+            let resp = channel
+                .routes()
+                .oneshot(request(
+                    &[
+                        ("From", "+15551234567"),
+                        ("Body", "Any news?"),
+                        ("MessageSid", "SM1"),
+                    ],
+                    true,
+                ))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+
+            use futures::StreamExt;
+            let msg = stream.next().await.unwrap();
+            assert_eq!(msg.channel, "sms");
+            assert_eq!(msg.user_id, "+15551234567");
+            assert_eq!(msg.user_name.as_deref(), Some("Jane Client"));
+            assert_eq!(msg.content, "Any news?");
+            assert_eq!(msg.metadata["sms_message_sid"], "SM1");
+            assert_eq!(msg.metadata["sms_client_language"], "es");
+        }
+
+        #[tokio::test]
+        async fn client_keywords_feed_consent_history_and_preferences_gate_sends() {
+            let (db, _dir) = crate::testing::test_db().await;
+            let client = db
+                .create_client(
+                    "default",
+                    &crate::db::CreateClientParams {
+                        name: "Jane Client".to_string(),
+                        client_type: crate::db::ClientType::Individual,
+                        email: None,
+                        phone: Some("(555) 123-4567".to_string()),
+                        address: None,
+                        notes: None,
+                    },
+                )
+                .await
+                .unwrap();
+            let channel = SmsChannel::new(config(), Arc::clone(&db)).unwrap();
+            let phone = "+15551234567";
+
+            channel
+                .routes()
+                .oneshot(request(&[("From", phone), ("Body", "START")], true))
+                .await
+                .unwrap();
+            let history = db
+                .list_client_communication_consents("default", client.id)
+                .await
+                .unwrap();
+            assert_eq!(history.len(), 1);
+            assert_eq!(history[0].channel, CommunicationChannel::Sms);
+            assert_eq!(history[0].status, CommunicationConsentStatus::Granted);
+            assert_eq!(history[0].source, "sms:START");
+
+            db.upsert_client_communication_preferences(
+                "default",
+                client.id,
+                &crate::db::UpsertClientCommunicationPreferencesParams {
+                    allowed_channels: vec![CommunicationChannel::Email],
+                    quiet_hours: Vec::new(),
+                    timezone: None,
+                    language: None,
+                    disclaimers: Vec::new(),
+                    updated_by: "default".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+            let err = channel
+                .broadcast(phone, OutgoingResponse::text("Reminder"))
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("does not accept sms"));
+        }
+    }
+}
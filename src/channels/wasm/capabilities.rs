@@ -4,10 +4,12 @@
 //! Channels have additional capabilities beyond tools: HTTP endpoint registration,
 //! message emission, and workspace write access within their namespace.
 
+use std::sync::Arc;
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
+use crate::integrations::transcription::TranscriptionService;
 use crate::tools::wasm::{Capabilities as ToolCapabilities, RateLimitConfig};
 
 /// Minimum allowed polling interval (30 seconds).
@@ -49,6 +51,9 @@ pub struct ChannelCapabilities {
 
     /// Callback timeout duration.
     pub callback_timeout: Duration,
+
+    /// Host transcription for `transcribe-audio` (injected at load time).
+    pub transcription: Option<Arc<TranscriptionService>>,
 }
 
 impl Default for ChannelCapabilities {
@@ -62,6 +67,7 @@ impl Default for ChannelCapabilities {
             emit_rate_limit: EmitRateLimitConfig::default(),
             max_message_size: 64 * 1024, // 64 KB
             callback_timeout: Duration::from_secs(30),
+            transcription: None,
         }
     }
 }
@@ -4,12 +4,20 @@
 //! - Message emission (queueing messages to send to the agent)
 //! - Workspace write access (scoped to channel namespace)
 //! - Rate limiting for message emission
+//! - Persistent key-value state (backed by the settings table)
+//! - Scheduled one-off callbacks
 
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tokio::sync::mpsc;
 
 use crate::channels::wasm::capabilities::{ChannelCapabilities, EmitRateLimitConfig};
 use crate::channels::wasm::error::WasmChannelError;
+use crate::db::SettingsStore;
 use crate::tools::wasm::{HostState, LogLevel};
+use crate::workspace::Workspace;
 
 /// Maximum emitted messages per callback execution.
 const MAX_EMITS_PER_EXECUTION: usize = 100;
@@ -17,6 +25,18 @@ const MAX_EMITS_PER_EXECUTION: usize = 100;
 /// Maximum message content size (64 KB).
 const MAX_MESSAGE_CONTENT_SIZE: usize = 64 * 1024;
 
+/// Maximum callbacks a single execution may schedule.
+pub const MAX_CALLBACKS_PER_EXECUTION: usize = 10;
+
+/// Maximum callbacks waiting to fire per channel; extras are dropped.
+pub const MAX_PENDING_CALLBACKS: usize = 100;
+
+/// Longest delay a scheduled callback may request (24 hours).
+const MAX_CALLBACK_DELAY_MS: u32 = 24 * 60 * 60 * 1000;
+
+/// Maximum scheduled callback payload size (4 KB).
+const MAX_CALLBACK_PAYLOAD_SIZE: usize = 4 * 1024;
+
 /// A message emitted by a WASM channel to be sent to the agent.
 #[derive(Debug, Clone)]
 pub struct EmittedMessage {
@@ -84,6 +104,16 @@ pub struct PendingWorkspaceWrite {
     pub content: String,
 }
 
+/// A callback requested via `schedule-callback`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledCallback {
+    /// How long to wait before calling `on-callback`.
+    pub delay: Duration,
+
+    /// Payload handed back to `on-callback`.
+    pub payload: String,
+}
+
 /// Host state for WASM channel callbacks.
 ///
 /// Maintains all side effects during callback execution and enforces limits.
@@ -104,6 +134,9 @@ pub struct ChannelHostState {
     /// Pending workspace writes.
     pending_writes: Vec<PendingWorkspaceWrite>,
 
+    /// Callbacks scheduled during this execution.
+    scheduled_callbacks: Vec<ScheduledCallback>,
+
     /// Emit count for rate limiting within this execution.
     emit_count: u32,
 
@@ -120,6 +153,7 @@ impl std::fmt::Debug for ChannelHostState {
             .field("channel_name", &self.channel_name)
             .field("emitted_messages_count", &self.emitted_messages.len())
             .field("pending_writes_count", &self.pending_writes.len())
+            .field("scheduled_callbacks_count", &self.scheduled_callbacks.len())
             .field("emit_count", &self.emit_count)
             .field("emit_enabled", &self.emit_enabled)
             .field("emits_dropped", &self.emits_dropped)
@@ -138,6 +172,7 @@ impl ChannelHostState {
             capabilities,
             emitted_messages: Vec::new(),
             pending_writes: Vec::new(),
+            scheduled_callbacks: Vec::new(),
             emit_count: 0,
             emit_enabled: true,
             emits_dropped: 0,
@@ -255,6 +290,43 @@ impl ChannelHostState {
         self.pending_writes.len()
     }
 
+    /// Schedule `on-callback` to run once after `delay_ms`.
+    ///
+    /// Callbacks are queued and handed to the channel's timer after the
+    /// current execution completes.
+    pub fn schedule_callback(&mut self, delay_ms: u32, payload: String) -> Result<(), String> {
+        if delay_ms > MAX_CALLBACK_DELAY_MS {
+            return Err(format!(
+                "Callback delay {}ms exceeds maximum of {}ms",
+                delay_ms, MAX_CALLBACK_DELAY_MS
+            ));
+        }
+        if payload.len() > MAX_CALLBACK_PAYLOAD_SIZE {
+            return Err(format!(
+                "Callback payload too large: {} bytes (max {})",
+                payload.len(),
+                MAX_CALLBACK_PAYLOAD_SIZE
+            ));
+        }
+        if self.scheduled_callbacks.len() >= MAX_CALLBACKS_PER_EXECUTION {
+            return Err(format!(
+                "At most {} callbacks may be scheduled per execution",
+                MAX_CALLBACKS_PER_EXECUTION
+            ));
+        }
+
+        self.scheduled_callbacks.push(ScheduledCallback {
+            delay: Duration::from_millis(delay_ms as u64),
+            payload,
+        });
+        Ok(())
+    }
+
+    /// Take all scheduled callbacks (clears the queue).
+    pub fn take_scheduled_callbacks(&mut self) -> Vec<ScheduledCallback> {
+        std::mem::take(&mut self.scheduled_callbacks)
+    }
+
     /// Log a message (delegates to base).
     pub fn log(
         &mut self,
@@ -311,6 +383,8 @@ impl ChannelHostState {
 /// inside `spawn_blocking`.
 pub struct ChannelWorkspaceStore {
     data: std::sync::RwLock<std::collections::HashMap<String, String>>,
+    /// Path prefix and writer queue for writes copied into the agent workspace.
+    mirror: Option<(String, mpsc::UnboundedSender<PendingWorkspaceWrite>)>,
 }
 
 impl ChannelWorkspaceStore {
@@ -318,6 +392,32 @@ impl ChannelWorkspaceStore {
     pub fn new() -> Self {
         Self {
             data: std::sync::RwLock::new(std::collections::HashMap::new()),
+            mirror: None,
+        }
+    }
+
+    /// Create a store that also copies writes under `prefix` (e.g.
+    /// `channels/whatsapp/media/`) into the agent workspace, so files a
+    /// channel ingests can be read and searched by the agent.
+    ///
+    /// Mirrored writes go through a background task in commit order; a
+    /// failed write is logged and does not affect the channel.
+    pub fn with_workspace_mirror(workspace: Arc<Workspace>, prefix: String) -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel::<PendingWorkspaceWrite>();
+        tokio::spawn(async move {
+            while let Some(write) = rx.recv().await {
+                if let Err(e) = workspace.write(&write.path, &write.content).await {
+                    tracing::warn!(
+                        path = %write.path,
+                        error = %e,
+                        "Failed to mirror channel write into workspace"
+                    );
+                }
+            }
+        });
+        Self {
+            data: std::sync::RwLock::new(std::collections::HashMap::new()),
+            mirror: Some((prefix, tx)),
         }
     }
 
@@ -336,6 +436,14 @@ impl ChannelWorkspaceStore {
                 data.insert(write.path.clone(), write.content.clone());
             }
         }
+        if let Some((ref prefix, ref tx)) = self.mirror {
+            for write in writes
+                .iter()
+                .filter(|w| w.path.starts_with(prefix.as_str()))
+            {
+                let _ = tx.send(write.clone());
+            }
+        }
     }
 }
 
@@ -345,6 +453,222 @@ impl crate::tools::wasm::WorkspaceReader for ChannelWorkspaceStore {
     }
 }
 
+/// Maximum key length for channel key-value state.
+const MAX_STATE_KEY_LEN: usize = 256;
+
+/// Maximum value size for channel key-value state (64 KB).
+const MAX_STATE_VALUE_SIZE: usize = 64 * 1024;
+
+/// Maximum number of keys a single channel may hold.
+const MAX_STATE_KEYS: usize = 1000;
+
+/// A state mutation queued for the settings-table writer.
+#[derive(Debug)]
+enum StateOp {
+    Set { key: String, value: String },
+    Delete { key: String },
+}
+
+/// Persistent key-value state for one WASM channel.
+///
+/// Host calls (`state-get`/`state-set`/`state-delete`) run inside
+/// `spawn_blocking`, so reads and writes go against an in-memory map
+/// guarded by a `std::sync::RwLock`. When opened with a settings store,
+/// every mutation is also queued to a background task that writes it to the
+/// settings table under `channel_state.<channel>.<key>`, in order. On the
+/// next start the map is reloaded from those rows, so cursors like the
+/// Telegram polling offset survive restarts.
+pub struct ChannelStateStore {
+    channel_name: String,
+    data: std::sync::RwLock<HashMap<String, String>>,
+    persist_tx: Option<mpsc::UnboundedSender<StateOp>>,
+}
+
+impl std::fmt::Debug for ChannelStateStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChannelStateStore")
+            .field("channel_name", &self.channel_name)
+            .field("persistent", &self.persist_tx.is_some())
+            .finish()
+    }
+}
+
+impl ChannelStateStore {
+    /// Create a state store that lives only as long as the channel.
+    pub fn in_memory(channel_name: impl Into<String>) -> Self {
+        Self {
+            channel_name: channel_name.into(),
+            data: std::sync::RwLock::new(HashMap::new()),
+            persist_tx: None,
+        }
+    }
+
+    /// Open a state store backed by the settings table.
+    ///
+    /// Loads any state saved by a previous run and spawns the writer task.
+    /// A failed load is logged and the channel starts with empty state.
+    pub async fn open(
+        channel_name: impl Into<String>,
+        settings: Arc<dyn SettingsStore>,
+        user_id: impl Into<String>,
+    ) -> Self {
+        let channel_name = channel_name.into();
+        let user_id = user_id.into();
+        let prefix = Self::settings_prefix(&channel_name);
+
+        let mut data = HashMap::new();
+        match settings.list_settings(&user_id).await {
+            Ok(rows) => {
+                for row in rows {
+                    if let Some(key) = row.key.strip_prefix(&prefix)
+                        && let Some(value) = row.value.as_str()
+                    {
+                        data.insert(key.to_string(), value.to_string());
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    channel = %channel_name,
+                    error = %e,
+                    "Failed to load channel state, starting empty"
+                );
+            }
+        }
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<StateOp>();
+        let writer_channel = channel_name.clone();
+        tokio::spawn(async move {
+            while let Some(op) = rx.recv().await {
+                let result = match &op {
+                    StateOp::Set { key, value } => settings
+                        .set_setting(
+                            &user_id,
+                            &format!("{prefix}{key}"),
+                            &serde_json::Value::String(value.clone()),
+                        )
+                        .await
+                        .map(|_| ()),
+                    StateOp::Delete { key } => settings
+                        .delete_setting(&user_id, &format!("{prefix}{key}"))
+                        .await
+                        .map(|_| ()),
+                };
+                if let Err(e) = result {
+                    tracing::warn!(
+                        channel = %writer_channel,
+                        op = ?op,
+                        error = %e,
+                        "Failed to persist channel state"
+                    );
+                }
+            }
+        });
+
+        Self {
+            channel_name,
+            data: std::sync::RwLock::new(data),
+            persist_tx: Some(tx),
+        }
+    }
+
+    /// Settings key prefix for a channel's state rows.
+    pub fn settings_prefix(channel_name: &str) -> String {
+        format!("channel_state.{channel_name}.")
+    }
+
+    /// Read a value.
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.data.read().ok()?.get(key).cloned()
+    }
+
+    /// Store a value, enforcing key, value, and key-count limits.
+    pub fn set(&self, key: &str, value: String) -> Result<(), String> {
+        validate_state_key(key)?;
+        if value.len() > MAX_STATE_VALUE_SIZE {
+            return Err(format!(
+                "State value too large: {} bytes (max {})",
+                value.len(),
+                MAX_STATE_VALUE_SIZE
+            ));
+        }
+
+        let mut data = self
+            .data
+            .write()
+            .map_err(|_| "Channel state lock poisoned".to_string())?;
+        if !data.contains_key(key) && data.len() >= MAX_STATE_KEYS {
+            return Err(format!(
+                "Channel state key limit reached (max {})",
+                MAX_STATE_KEYS
+            ));
+        }
+        data.insert(key.to_string(), value.clone());
+        drop(data);
+
+        self.persist(StateOp::Set {
+            key: key.to_string(),
+            value,
+        });
+        Ok(())
+    }
+
+    /// Remove a key. Returns true if it existed.
+    pub fn delete(&self, key: &str) -> Result<bool, String> {
+        validate_state_key(key)?;
+        let removed = self
+            .data
+            .write()
+            .map_err(|_| "Channel state lock poisoned".to_string())?
+            .remove(key)
+            .is_some();
+        if removed {
+            self.persist(StateOp::Delete {
+                key: key.to_string(),
+            });
+        }
+        Ok(removed)
+    }
+
+    /// Number of keys currently held.
+    pub fn len(&self) -> usize {
+        self.data.read().map(|d| d.len()).unwrap_or(0)
+    }
+
+    /// Whether the store holds no keys.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn persist(&self, op: StateOp) {
+        if let Some(ref tx) = self.persist_tx
+            && tx.send(op).is_err()
+        {
+            tracing::warn!(
+                channel = %self.channel_name,
+                "Channel state writer stopped; change kept in memory only"
+            );
+        }
+    }
+}
+
+/// Validate a state key: 1-256 chars of `[A-Za-z0-9._:-]`.
+fn validate_state_key(key: &str) -> Result<(), String> {
+    if key.is_empty() || key.len() > MAX_STATE_KEY_LEN {
+        return Err(format!(
+            "State key must be 1-{} characters",
+            MAX_STATE_KEY_LEN
+        ));
+    }
+    if !key
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | ':' | '-'))
+    {
+        return Err(format!("Invalid state key: {key}"));
+    }
+    Ok(())
+}
+
 /// Rate limiter for channel message emission.
 ///
 /// Tracks emission rates across multiple executions.
@@ -594,4 +918,135 @@ mod tests {
             Some("200".to_string())
         );
     }
+
+    #[test]
+    fn test_channel_state_store_limits() {
+        use crate::channels::wasm::host::{ChannelStateStore, MAX_STATE_KEYS};
+
+        let store = ChannelStateStore::in_memory("telegram");
+        store.set("offset", "42".to_string()).unwrap();
+        assert_eq!(store.get("offset"), Some("42".to_string()));
+
+        assert!(store.set("", "x".to_string()).is_err());
+        assert!(store.set("../escape", "x".to_string()).is_err());
+        assert!(store.set("big", "x".repeat(64 * 1024 + 1)).is_err());
+
+        for i in 1..MAX_STATE_KEYS {
+            store.set(&format!("k{i}"), String::new()).unwrap();
+        }
+        assert!(store.set("one-too-many", String::new()).is_err());
+        // Overwriting an existing key is still allowed at the limit.
+        store.set("offset", "43".to_string()).unwrap();
+
+        assert!(store.delete("offset").unwrap());
+        assert!(!store.delete("offset").unwrap());
+        assert_eq!(store.len(), MAX_STATE_KEYS - 1);
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn test_channel_state_store_survives_reopen() {
+        use std::sync::Arc;
+
+        use crate::channels::wasm::host::ChannelStateStore;
+        use crate::db::SettingsStore;
+
+        let (db, _dir) = crate::testing::test_db().await;
+        let settings: Arc<dyn SettingsStore> = db.clone();
+
+        let store = ChannelStateStore::open("telegram", Arc::clone(&settings), "default").await;
+        store.set("scratch", "x".to_string()).unwrap();
+        store.delete("scratch").unwrap();
+        store.set("offset", "103".to_string()).unwrap();
+
+        let key = format!("{}offset", ChannelStateStore::settings_prefix("telegram"));
+        for _ in 0..100 {
+            if db.get_setting("default", &key).await.unwrap().is_some() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        // The writer applies ops in order, so the earlier delete has landed
+        // once the offset row is visible.
+
+        let reopened = ChannelStateStore::open("telegram", settings.clone(), "default").await;
+        assert_eq!(reopened.get("offset"), Some("103".to_string()));
+        assert_eq!(reopened.get("scratch"), None);
+
+        // Other channels do not see telegram's state.
+        let other = ChannelStateStore::open("slack", settings, "default").await;
+        assert!(other.is_empty());
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn test_workspace_mirror_copies_media_writes() {
+        use std::sync::Arc;
+
+        use crate::channels::wasm::host::{ChannelWorkspaceStore, PendingWorkspaceWrite};
+        use crate::workspace::Workspace;
+
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", db));
+        let store = ChannelWorkspaceStore::with_workspace_mirror(
+            Arc::clone(&workspace),
+            "channels/whatsapp/media/".to_string(),
+        );
+
+        store.commit_writes(&[
+            PendingWorkspaceWrite {
+                path: "channels/whatsapp/state/owner_id".to_string(),
+                content: "15551234567".to_string(),
+            },
+            PendingWorkspaceWrite {
+                path: "channels/whatsapp/media/wamid_1.md".to_string(),
+                content: "# WhatsApp image".to_string(),
+            },
+        ]);
+
+        let mut mirrored = None;
+        for _ in 0..100 {
+            if let Ok(doc) = workspace.read("channels/whatsapp/media/wamid_1.md").await {
+                mirrored = Some(doc.content);
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(mirrored.as_deref(), Some("# WhatsApp image"));
+        // Writes outside the mirrored prefix stay channel-local.
+        assert!(
+            workspace
+                .read("channels/whatsapp/state/owner_id")
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_schedule_callback_limits() {
+        use crate::channels::wasm::host::MAX_CALLBACKS_PER_EXECUTION;
+
+        let caps = ChannelCapabilities::for_channel("telegram");
+        let mut state = ChannelHostState::new("telegram", caps);
+
+        state
+            .schedule_callback(4_000, "typing:123".to_string())
+            .unwrap();
+        assert!(
+            state
+                .schedule_callback(24 * 60 * 60 * 1000 + 1, String::new())
+                .is_err()
+        );
+        assert!(state.schedule_callback(0, "x".repeat(4097)).is_err());
+        for _ in 1..MAX_CALLBACKS_PER_EXECUTION {
+            state.schedule_callback(0, String::new()).unwrap();
+        }
+        assert!(state.schedule_callback(0, String::new()).is_err());
+
+        let callbacks = state.take_scheduled_callbacks();
+        assert_eq!(callbacks.len(), MAX_CALLBACKS_PER_EXECUTION);
+        assert_eq!(callbacks[0].delay, std::time::Duration::from_secs(4));
+        assert_eq!(callbacks[0].payload, "typing:123");
+        assert!(state.take_scheduled_callbacks().is_empty());
+    }
 }
@@ -13,15 +13,25 @@ use tokio::fs;
 
 use crate::channels::wasm::capabilities::ChannelCapabilities;
 use crate::channels::wasm::error::WasmChannelError;
+use crate::channels::wasm::host::ChannelStateStore;
 use crate::channels::wasm::runtime::WasmChannelRuntime;
 use crate::channels::wasm::schema::ChannelCapabilitiesFile;
 use crate::channels::wasm::wrapper::WasmChannel;
+use crate::db::SettingsStore;
+use crate::integrations::transcription::TranscriptionService;
 use crate::pairing::PairingStore;
+use crate::workspace::Workspace;
 
 /// Loads WASM channels from the filesystem.
 pub struct WasmChannelLoader {
     runtime: Arc<WasmChannelRuntime>,
     pairing_store: Arc<PairingStore>,
+    /// Backing store for channel key-value state, with the owning user.
+    settings_store: Option<(Arc<dyn SettingsStore>, String)>,
+    /// Agent workspace that receives files channels save under `media/`.
+    workspace: Option<Arc<Workspace>>,
+    /// Voice note transcription offered to channels via `transcribe-audio`.
+    transcription: Option<Arc<TranscriptionService>>,
 }
 
 impl WasmChannelLoader {
@@ -30,9 +40,36 @@ impl WasmChannelLoader {
         Self {
             runtime,
             pairing_store,
+            settings_store: None,
+            workspace: None,
+            transcription: None,
         }
     }
 
+    /// Persist channel key-value state in the settings table for `user_id`.
+    ///
+    /// Without this, `state-set` values only last until the channel stops.
+    pub fn with_settings_store(
+        mut self,
+        store: Arc<dyn SettingsStore>,
+        user_id: impl Into<String>,
+    ) -> Self {
+        self.settings_store = Some((store, user_id.into()));
+        self
+    }
+
+    /// Mirror files channels write under `media/` into the agent workspace.
+    pub fn with_workspace(mut self, workspace: Arc<Workspace>) -> Self {
+        self.workspace = Some(workspace);
+        self
+    }
+
+    /// Let loaded channels transcribe voice notes through `service`.
+    pub fn with_transcription(mut self, service: Arc<TranscriptionService>) -> Self {
+        self.transcription = Some(service);
+        self
+    }
+
     /// Load a single WASM channel from a file pair.
     ///
     /// Expects:
@@ -119,13 +156,23 @@ impl WasmChannelLoader {
             .await?;
 
         // Create the channel
-        let channel = WasmChannel::new(
+        let mut channel = WasmChannel::new(
             self.runtime.clone(),
             prepared,
             capabilities,
             config_json,
             self.pairing_store.clone(),
         );
+        if let Some((ref store, ref user_id)) = self.settings_store {
+            let state = ChannelStateStore::open(name, Arc::clone(store), user_id.clone()).await;
+            channel = channel.with_state_store(Arc::new(state));
+        }
+        if let Some(ref workspace) = self.workspace {
+            channel = channel.with_workspace_mirror(Arc::clone(workspace));
+        }
+        if let Some(ref service) = self.transcription {
+            channel = channel.with_transcription(Arc::clone(service));
+        }
 
         tracing::info!(
             name = name,
@@ -255,6 +302,20 @@ impl LoadedChannel {
             .map(|f| f.webhook_secret_name())
             .unwrap_or_else(|| format!("{}_webhook_secret", self.channel.channel_name()))
     }
+
+    /// Get the query parameter that may carry the webhook secret.
+    pub fn webhook_verify_token_param(&self) -> Option<&str> {
+        self.capabilities_file
+            .as_ref()
+            .and_then(|f| f.webhook_verify_token_param())
+    }
+
+    /// Get the body-signature header and signing secret name from capabilities.
+    pub fn webhook_signature(&self) -> Option<(&str, &str)> {
+        self.capabilities_file
+            .as_ref()
+            .and_then(|f| f.webhook_signature())
+    }
 }
 
 /// Results from loading multiple channels.
@@ -1,7 +1,8 @@
 //! HTTP router for WASM channel webhooks.
 //!
 //! Routes incoming HTTP requests to the appropriate WASM channel based on
-//! registered paths. Handles secret validation at the host level.
+//! registered paths. Handles secret validation at the host level, including
+//! HMAC-SHA256 body signatures for channels that sign their webhooks.
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -10,15 +11,29 @@ use axum::{
     Json, Router,
     body::Bytes,
     extract::{Path, Query, State},
-    http::{HeaderMap, Method, StatusCode},
-    response::IntoResponse,
+    http::{HeaderMap, Method, StatusCode, header},
+    response::{IntoResponse, Response},
     routing::{get, post},
 };
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
 use tokio::sync::RwLock;
 
 use crate::channels::wasm::wrapper::WasmChannel;
 
+type HmacSha256 = Hmac<Sha256>;
+
+/// Body-signature settings for a channel whose provider signs webhooks.
+#[derive(Debug, Clone)]
+struct WebhookSigning {
+    /// Header carrying `sha256=<hex>` (e.g., "X-Hub-Signature-256").
+    header: String,
+    /// HMAC key (e.g., the Meta app secret).
+    secret: String,
+}
+
 /// A registered HTTP endpoint for a WASM channel.
 #[derive(Debug, Clone)]
 pub struct RegisteredEndpoint {
@@ -42,6 +57,10 @@ pub struct WasmChannelRouter {
     secrets: RwLock<HashMap<String, String>>,
     /// Webhook secret header names by channel name (e.g., "X-Telegram-Bot-Api-Secret-Token").
     secret_headers: RwLock<HashMap<String, String>>,
+    /// Query parameters that may carry the secret, by channel name (e.g., "hub.verify_token").
+    verify_token_params: RwLock<HashMap<String, String>>,
+    /// Body-signature settings by channel name.
+    signing: RwLock<HashMap<String, WebhookSigning>>,
 }
 
 impl WasmChannelRouter {
@@ -52,6 +71,8 @@ impl WasmChannelRouter {
             path_to_channel: RwLock::new(HashMap::new()),
             secrets: RwLock::new(HashMap::new()),
             secret_headers: RwLock::new(HashMap::new()),
+            verify_token_params: RwLock::new(HashMap::new()),
+            signing: RwLock::new(HashMap::new()),
         }
     }
 
@@ -110,6 +131,54 @@ impl WasmChannelRouter {
             .unwrap_or_else(|| "X-Webhook-Secret".to_string())
     }
 
+    /// Accept the webhook secret from a named query parameter as well as the
+    /// default `secret` parameter (used for subscription handshakes).
+    pub async fn set_verify_token_param(&self, channel_name: &str, param: String) {
+        self.verify_token_params
+            .write()
+            .await
+            .insert(channel_name.to_string(), param);
+    }
+
+    /// Require POST bodies for a channel to carry a valid HMAC-SHA256
+    /// signature in `header`, keyed by `secret`.
+    ///
+    /// Signed POSTs do not also need the plain webhook secret; it is still
+    /// checked on GET (e.g., WhatsApp's verify-token handshake).
+    pub async fn set_signing_secret(&self, channel_name: &str, header: String, secret: String) {
+        self.signing
+            .write()
+            .await
+            .insert(channel_name.to_string(), WebhookSigning { header, secret });
+        tracing::info!(
+            channel = %channel_name,
+            "Enabled webhook body signature validation for channel"
+        );
+    }
+
+    /// Check whether a channel has body signing configured.
+    pub async fn requires_signature(&self, channel_name: &str) -> bool {
+        self.signing.read().await.contains_key(channel_name)
+    }
+
+    /// Validate a body signature for a channel. Channels without signing
+    /// configured always validate.
+    pub async fn validate_signature(
+        &self,
+        channel_name: &str,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> bool {
+        let signing = self.signing.read().await;
+        let Some(signing) = signing.get(channel_name) else {
+            return true;
+        };
+        headers
+            .get(&signing.header)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|provided| verify_body_signature(&signing.secret, body, provided))
+    }
+
     /// Update the webhook secret for an already-registered channel.
     ///
     /// This is used when credentials are saved after a channel was registered
@@ -130,6 +199,8 @@ impl WasmChannelRouter {
         self.channels.write().await.remove(channel_name);
         self.secrets.write().await.remove(channel_name);
         self.secret_headers.write().await.remove(channel_name);
+        self.verify_token_params.write().await.remove(channel_name);
+        self.signing.write().await.remove(channel_name);
 
         // Remove all paths for this channel
         self.path_to_channel
@@ -155,7 +226,7 @@ impl WasmChannelRouter {
     pub async fn validate_secret(&self, channel_name: &str, provided: &str) -> bool {
         let secrets = self.secrets.read().await;
         match secrets.get(channel_name) {
-            Some(expected) => expected == provided,
+            Some(expected) => bool::from(expected.as_bytes().ct_eq(provided.as_bytes())),
             None => true, // No secret required
         }
     }
@@ -176,6 +247,28 @@ impl WasmChannelRouter {
     }
 }
 
+/// Check a `sha256=<hex>` signature header against `HMAC-SHA256(secret, body)`
+/// in constant time.
+fn verify_body_signature(secret: &str, body: &[u8], provided: &str) -> bool {
+    let Some(provided_hex) = provided.trim().strip_prefix("sha256=") else {
+        return false;
+    };
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    let expected: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    bool::from(
+        expected
+            .as_bytes()
+            .ct_eq(provided_hex.to_ascii_lowercase().as_bytes()),
+    )
+}
+
 impl Default for WasmChannelRouter {
     fn default() -> Self {
         Self::new()
@@ -242,7 +335,7 @@ async fn webhook_handler(
     Query(query): Query<HashMap<String, String>>,
     headers: HeaderMap,
     body: Bytes,
-) -> impl IntoResponse {
+) -> Response {
     let full_path = format!("/webhook/{}", path);
 
     tracing::info!(
@@ -266,7 +359,8 @@ async fn webhook_handler(
                     "error": "Channel not found for path",
                     "path": full_path
                 })),
-            );
+            )
+                .into_response();
         }
     };
 
@@ -277,15 +371,50 @@ async fn webhook_handler(
 
     let channel_name = channel.channel_name();
 
+    // Signed channels authenticate POST bodies by HMAC instead of the plain secret
+    let signed_post = method == Method::POST && state.router.requires_signature(channel_name).await;
+    if signed_post {
+        if !state
+            .router
+            .validate_signature(channel_name, &headers, &body)
+            .await
+        {
+            tracing::warn!(
+                channel = %channel_name,
+                "Webhook signature validation failed"
+            );
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({
+                    "error": "Invalid webhook signature"
+                })),
+            )
+                .into_response();
+        }
+        tracing::debug!(channel = %channel_name, "Webhook signature validated");
+    }
+
     // Check if secret is required
-    if state.router.requires_secret(channel_name).await {
+    if !signed_post && state.router.requires_secret(channel_name).await {
         // Get the secret header name for this channel (from capabilities or default)
         let secret_header_name = state.router.get_secret_header(channel_name).await;
+        let verify_token_param = state
+            .router
+            .verify_token_params
+            .read()
+            .await
+            .get(channel_name)
+            .cloned();
 
         // Try to get secret from query param or the channel's configured header
         let provided_secret = query
             .get("secret")
             .cloned()
+            .or_else(|| {
+                verify_token_param
+                    .as_ref()
+                    .and_then(|param| query.get(param).cloned())
+            })
             .or_else(|| {
                 headers
                     .get(&secret_header_name)
@@ -323,7 +452,8 @@ async fn webhook_handler(
                         Json(serde_json::json!({
                             "error": "Invalid webhook secret"
                         })),
-                    );
+                    )
+                        .into_response();
                 }
                 tracing::debug!(channel = %channel_name, "Webhook secret validated");
             }
@@ -337,7 +467,8 @@ async fn webhook_handler(
                     Json(serde_json::json!({
                         "error": "Webhook secret required"
                     })),
-                );
+                )
+                    .into_response();
             }
         }
     }
@@ -353,7 +484,7 @@ async fn webhook_handler(
         .collect();
 
     // Call the WASM channel
-    let secret_validated = state.router.requires_secret(channel_name).await;
+    let secret_validated = signed_post || state.router.requires_secret(channel_name).await;
 
     tracing::info!(
         channel = %channel_name,
@@ -383,15 +514,25 @@ async fn webhook_handler(
                 "WASM channel on_http_request completed successfully"
             );
 
-            // Build response with headers
-            let body_json: serde_json::Value = serde_json::from_slice(&response.body)
-                .unwrap_or_else(|_| {
-                    serde_json::json!({
-                        "raw": String::from_utf8_lossy(&response.body).to_string()
-                    })
-                });
-
-            (status, Json(body_json))
+            // JSON bodies pass through as JSON. Anything else (e.g., a plain-text
+            // hub.challenge echo) is returned verbatim with the channel's Content-Type.
+            match serde_json::from_slice::<serde_json::Value>(&response.body) {
+                Ok(body_json) => (status, Json(body_json)).into_response(),
+                Err(_) => {
+                    let content_type = response
+                        .headers
+                        .iter()
+                        .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+                        .map(|(_, v)| v.clone())
+                        .unwrap_or_else(|| "text/plain; charset=utf-8".to_string());
+                    (
+                        status,
+                        [(header::CONTENT_TYPE, content_type)],
+                        response.body,
+                    )
+                        .into_response()
+                }
+            }
         }
         Err(e) => {
             tracing::error!(
@@ -406,6 +547,7 @@ async fn webhook_handler(
                     "details": e.to_string()
                 })),
             )
+                .into_response()
         }
     }
 }
@@ -616,6 +758,68 @@ mod tests {
         assert!(channels.contains(&"telegram".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_router_signature_validation() {
+        use axum::http::HeaderMap;
+        use hmac::Mac;
+
+        let router = WasmChannelRouter::new();
+        router
+            .register(create_test_channel("whatsapp"), vec![], None, None)
+            .await;
+        let body = br#"{"object":"whatsapp_business_account"}"#;
+
+        // No signing configured: everything validates
+        assert!(!router.requires_signature("whatsapp").await);
+        assert!(
+            router
+                .validate_signature("whatsapp", &HeaderMap::new(), body)
+                .await
+        );
+
+        router
+            .set_signing_secret(
+                "whatsapp",
+                "X-Hub-Signature-256".to_string(),
+                "app-secret".to_string(),
+            )
+            .await;
+        assert!(router.requires_signature("whatsapp").await);
+
+        let mut mac = super::HmacSha256::new_from_slice(b"app-secret").unwrap();
+        mac.update(body);
+        let sig: String = mac
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "X-Hub-Signature-256",
+            format!("sha256={sig}").parse().unwrap(),
+        );
+        assert!(router.validate_signature("whatsapp", &headers, body).await);
+
+        // Tampered body, missing prefix, and missing header all fail
+        assert!(
+            !router
+                .validate_signature("whatsapp", &headers, b"{\"object\":\"x\"}")
+                .await
+        );
+        headers.insert("X-Hub-Signature-256", sig.parse().unwrap());
+        assert!(!router.validate_signature("whatsapp", &headers, body).await);
+        assert!(
+            !router
+                .validate_signature("whatsapp", &HeaderMap::new(), body)
+                .await
+        );
+
+        router.unregister("whatsapp").await;
+        assert!(!router.requires_signature("whatsapp").await);
+    }
+
     #[tokio::test]
     async fn test_router_secret_header() {
         let router = WasmChannelRouter::new();
@@ -122,6 +122,31 @@ impl ChannelCapabilitiesFile {
             .and_then(|w| w.secret_name.clone())
             .unwrap_or_else(|| format!("{}_webhook_secret", self.name))
     }
+
+    /// Get the query parameter that may carry the webhook secret.
+    pub fn webhook_verify_token_param(&self) -> Option<&str> {
+        self.webhook_schema()
+            .and_then(|w| w.verify_token_param.as_deref())
+    }
+
+    /// Get the body-signature header and signing secret name, if the channel
+    /// signs its webhooks. The header defaults to "X-Hub-Signature-256".
+    pub fn webhook_signature(&self) -> Option<(&str, &str)> {
+        let webhook = self.webhook_schema()?;
+        let secret_name = webhook.signature_secret_name.as_deref()?;
+        let header = webhook
+            .signature_header
+            .as_deref()
+            .unwrap_or("X-Hub-Signature-256");
+        Some((header, secret_name))
+    }
+
+    fn webhook_schema(&self) -> Option<&WebhookSchema> {
+        self.capabilities
+            .channel
+            .as_ref()
+            .and_then(|c| c.webhook.as_ref())
+    }
 }
 
 /// Schema for channel capabilities.
@@ -230,6 +255,21 @@ pub struct WebhookSchema {
     /// Default: "{channel_name}_webhook_secret"
     #[serde(default)]
     pub secret_name: Option<String>,
+
+    /// Query parameter that carries the secret on subscription handshakes
+    /// (e.g., WhatsApp's "hub.verify_token").
+    #[serde(default)]
+    pub verify_token_param: Option<String>,
+
+    /// HTTP header carrying an HMAC-SHA256 signature of the request body,
+    /// formatted as `sha256=<hex>` (e.g., "X-Hub-Signature-256").
+    #[serde(default)]
+    pub signature_header: Option<String>,
+
+    /// Secret name in secrets store for the body signing key.
+    /// Signature checking is enabled only when this is set.
+    #[serde(default)]
+    pub signature_secret_name: Option<String>,
 }
 
 /// Setup configuration schema.
@@ -547,6 +587,32 @@ mod tests {
         let file = ChannelCapabilitiesFile::from_json(json).unwrap();
         assert_eq!(file.webhook_secret_header(), None);
         assert_eq!(file.webhook_secret_name(), "mybot_webhook_secret");
+        assert_eq!(file.webhook_verify_token_param(), None);
+        assert_eq!(file.webhook_signature(), None);
+    }
+
+    #[test]
+    fn test_webhook_signature_schema() {
+        let json = r#"{
+            "name": "whatsapp",
+            "capabilities": {
+                "channel": {
+                    "allowed_paths": ["/webhook/whatsapp"],
+                    "webhook": {
+                        "secret_name": "whatsapp_verify_token",
+                        "verify_token_param": "hub.verify_token",
+                        "signature_secret_name": "whatsapp_app_secret"
+                    }
+                }
+            }
+        }"#;
+
+        let file = ChannelCapabilitiesFile::from_json(json).unwrap();
+        assert_eq!(file.webhook_verify_token_param(), Some("hub.verify_token"));
+        assert_eq!(
+            file.webhook_signature(),
+            Some(("X-Hub-Signature-256", "whatsapp_app_secret"))
+        );
     }
 
     #[test]
@@ -1,8 +1,8 @@
 //! WASM channel wrapper implementing the Channel trait.
 //!
 //! Wraps a prepared WASM channel module and provides the Channel interface.
-//! Each callback (on_start, on_http_request, on_poll, on_respond) creates
-//! a fresh WASM instance for isolation.
+//! Each callback (on_start, on_http_request, on_poll, on_respond, on_approval,
+//! on_callback) creates a fresh WASM instance for isolation.
 //!
 //! # Architecture
 //!
@@ -22,6 +22,7 @@
 //! │   │  │   limiter   │  │      ChannelHostState        │   │  │
 //! │   │  └─────────────┘  │  - emitted_messages          │   │  │
 //! │   │                   │  - pending_writes            │   │  │
+//! │   │                   │  - scheduled_callbacks       │   │  │
 //! │   │                   │  - base HostState (logging)  │   │  │
 //! │   │                   └──────────────────────────────┘   │  │
 //! │   └──────────────────────────────────────────────────────┘  │
@@ -43,17 +44,20 @@ use wasmtime_wasi::{ResourceTable, WasiCtx, WasiCtxBuilder, WasiView};
 use crate::channels::wasm::capabilities::ChannelCapabilities;
 use crate::channels::wasm::error::WasmChannelError;
 use crate::channels::wasm::host::{
-    ChannelEmitRateLimiter, ChannelHostState, ChannelWorkspaceStore, EmittedMessage,
+    ChannelEmitRateLimiter, ChannelHostState, ChannelStateStore, ChannelWorkspaceStore,
+    EmittedMessage, MAX_PENDING_CALLBACKS, ScheduledCallback,
 };
 use crate::channels::wasm::router::RegisteredEndpoint;
 use crate::channels::wasm::runtime::{PreparedChannelModule, WasmChannelRuntime};
 use crate::channels::wasm::schema::ChannelConfig;
 use crate::channels::{Channel, IncomingMessage, MessageStream, OutgoingResponse, StatusUpdate};
 use crate::error::ChannelError;
+use crate::integrations::transcription::{TranscriptionService, VoiceNote};
 use crate::pairing::PairingStore;
 use crate::safety::LeakDetector;
 use crate::tools::wasm::LogLevel;
 use crate::tools::wasm::WasmResourceLimiter;
+use crate::workspace::Workspace;
 
 // Generate component model bindings from the WIT file
 wasmtime::component::bindgen!({
@@ -78,6 +82,8 @@ struct ChannelStoreData {
     credentials: HashMap<String, String>,
     /// Pairing store for DM pairing (guest access control).
     pairing_store: Arc<PairingStore>,
+    /// Persistent key-value state for this channel.
+    state_store: Arc<ChannelStateStore>,
     /// Dedicated tokio runtime for HTTP requests, lazily initialized.
     /// Reused across multiple `http_request` calls within one execution.
     http_runtime: Option<tokio::runtime::Runtime>,
@@ -90,6 +96,7 @@ impl ChannelStoreData {
         capabilities: ChannelCapabilities,
         credentials: HashMap<String, String>,
         pairing_store: Arc<PairingStore>,
+        state_store: Arc<ChannelStateStore>,
     ) -> Self {
         // Create a minimal WASI context (no filesystem, no env vars for security)
         let wasi = WasiCtxBuilder::new().build();
@@ -101,10 +108,29 @@ impl ChannelStoreData {
             table: ResourceTable::new(),
             credentials,
             pairing_store,
+            state_store,
             http_runtime: None,
         }
     }
 
+    /// Dedicated single-threaded runtime for async host calls.
+    ///
+    /// We're inside spawn_blocking, so we can't rely on the main runtime's
+    /// I/O driver (it may be busy with WASM compilation or other startup work).
+    /// A dedicated runtime gives us our own I/O driver and avoids contention.
+    /// The runtime is lazily created and reused across calls within one execution.
+    fn http_runtime(&mut self) -> Result<&tokio::runtime::Runtime, String> {
+        if self.http_runtime.is_none() {
+            self.http_runtime = Some(
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|e| format!("Failed to create HTTP runtime: {e}"))?,
+            );
+        }
+        Ok(self.http_runtime.as_ref().expect("just initialized"))
+    }
+
     /// Inject credentials into a string by replacing placeholders.
     ///
     /// Replaces patterns like `{TELEGRAM_BOT_TOKEN}` or `{WHATSAPP_ACCESS_TOKEN}`
@@ -290,19 +316,7 @@ impl near::agent::channel_host::Host for ChannelStoreData {
             .unwrap_or(10 * 1024 * 1024);
 
         // Make the HTTP request using a dedicated single-threaded runtime.
-        // We're inside spawn_blocking, so we can't rely on the main runtime's
-        // I/O driver (it may be busy with WASM compilation or other startup work).
-        // A dedicated runtime gives us our own I/O driver and avoids contention.
-        // The runtime is lazily created and reused across calls within one execution.
-        if self.http_runtime.is_none() {
-            self.http_runtime = Some(
-                tokio::runtime::Builder::new_current_thread()
-                    .enable_all()
-                    .build()
-                    .map_err(|e| format!("Failed to create HTTP runtime: {e}"))?,
-            );
-        }
-        let rt = self.http_runtime.as_ref().expect("just initialized");
+        let rt = self.http_runtime()?;
         let result = rt.block_on(async {
             let client = reqwest::Client::builder()
                 .connect_timeout(std::time::Duration::from_secs(10))
@@ -494,6 +508,54 @@ impl near::agent::channel_host::Host for ChannelStoreData {
             .read_allow_from(&channel)
             .map_err(|e| e.to_string())
     }
+
+    fn state_get(&mut self, key: String) -> Option<String> {
+        self.state_store.get(&key)
+    }
+
+    fn state_set(&mut self, key: String, value: String) -> Result<(), String> {
+        self.state_store.set(&key, value)
+    }
+
+    fn state_delete(&mut self, key: String) -> Result<bool, String> {
+        self.state_store.delete(&key)
+    }
+
+    fn schedule_callback(&mut self, delay_ms: u32, payload: String) -> Result<(), String> {
+        self.host_state.schedule_callback(delay_ms, payload)
+    }
+
+    fn transcribe_audio(
+        &mut self,
+        audio: Vec<u8>,
+        mime_type: String,
+        sender: String,
+        audio_ref: String,
+    ) -> Result<String, String> {
+        let Some(service) = self.host_state.capabilities().transcription.clone() else {
+            return Err("transcription is not configured".to_string());
+        };
+        let channel = self.host_state.channel_name().to_string();
+        tracing::debug!(
+            channel = %channel,
+            bytes = audio.len(),
+            mime_type = %mime_type,
+            "WASM channel transcribing voice note"
+        );
+        let rt = self.http_runtime()?;
+        let transcript = rt
+            .block_on(service.transcribe(audio, &mime_type))
+            .map_err(|e| e.to_string())?;
+        service.record(VoiceNote {
+            channel,
+            sender,
+            audio_ref,
+            mime_type,
+            transcript: transcript.clone(),
+            received_at: chrono::Utc::now(),
+        });
+        Ok(transcript)
+    }
 }
 
 /// A WASM-based channel implementing the Channel trait.
@@ -553,6 +615,20 @@ pub struct WasmChannel {
     /// In-memory workspace store persisting writes across callback invocations.
     /// Ensures WASM channels can maintain state (e.g., polling offsets) between ticks.
     workspace_store: Arc<ChannelWorkspaceStore>,
+
+    /// Key-value state exposed through `state-get`/`state-set`/`state-delete`.
+    /// In-memory by default; see [`WasmChannel::with_state_store`].
+    state_store: Arc<ChannelStateStore>,
+
+    /// Queue of callbacks requested via `schedule-callback`. Callbacks queued
+    /// before `start()` wait until the timer loop is running.
+    callback_tx: mpsc::UnboundedSender<ScheduledCallback>,
+
+    /// Receiving end of `callback_tx`, held by the timer loop while it runs.
+    callback_rx: Arc<tokio::sync::Mutex<mpsc::UnboundedReceiver<ScheduledCallback>>>,
+
+    /// Timer loop shutdown signal sender (keeps the loop alive while held).
+    callback_shutdown_tx: RwLock<Option<oneshot::Sender<()>>>,
 }
 
 impl WasmChannel {
@@ -566,6 +642,8 @@ impl WasmChannel {
     ) -> Self {
         let name = prepared.name.clone();
         let rate_limiter = ChannelEmitRateLimiter::new(capabilities.emit_rate_limit.clone());
+        let state_store = Arc::new(ChannelStateStore::in_memory(name.clone()));
+        let (callback_tx, callback_rx) = mpsc::unbounded_channel();
 
         Self {
             name,
@@ -584,9 +662,37 @@ impl WasmChannel {
             typing_task: RwLock::new(None),
             pairing_store,
             workspace_store: Arc::new(ChannelWorkspaceStore::new()),
+            state_store,
+            callback_tx,
+            callback_rx: Arc::new(tokio::sync::Mutex::new(callback_rx)),
+            callback_shutdown_tx: RwLock::new(None),
         }
     }
 
+    /// Replace the channel's key-value state store (e.g. with one backed by
+    /// the settings table so state survives restarts).
+    pub fn with_state_store(mut self, state_store: Arc<ChannelStateStore>) -> Self {
+        self.state_store = state_store;
+        self
+    }
+
+    /// Copy files the channel writes under `media/` in its namespace into the
+    /// agent workspace (e.g. WhatsApp attachments land in
+    /// `channels/whatsapp/media/`).
+    pub fn with_workspace_mirror(mut self, workspace: Arc<Workspace>) -> Self {
+        let prefix = self.capabilities.prefix_workspace_path("media/");
+        self.workspace_store = Arc::new(ChannelWorkspaceStore::with_workspace_mirror(
+            workspace, prefix,
+        ));
+        self
+    }
+
+    /// Let the channel transcribe voice notes through the host.
+    pub fn with_transcription(mut self, service: Arc<TranscriptionService>) -> Self {
+        self.capabilities.transcription = Some(service);
+        self
+    }
+
     /// Update the channel config before starting.
     ///
     /// Merges the provided values into the existing config JSON.
@@ -686,6 +792,7 @@ impl WasmChannel {
         capabilities: &ChannelCapabilities,
         credentials: HashMap<String, String>,
         pairing_store: Arc<PairingStore>,
+        state_store: Arc<ChannelStateStore>,
     ) -> Result<Store<ChannelStoreData>, WasmChannelError> {
         let engine = runtime.engine();
         let limits = &prepared.limits;
@@ -697,6 +804,7 @@ impl WasmChannel {
             capabilities.clone(),
             credentials,
             pairing_store,
+            state_store,
         );
         let mut store = Store::new(engine, store_data);
 
@@ -807,6 +915,7 @@ impl WasmChannel {
         let channel_name = self.name.clone();
         let credentials = self.get_credentials().await;
         let pairing_store = self.pairing_store.clone();
+        let state_store = self.state_store.clone();
         let workspace_store = self.workspace_store.clone();
 
         // Execute in blocking task with timeout
@@ -818,6 +927,7 @@ impl WasmChannel {
                     &capabilities,
                     credentials,
                     pairing_store,
+                    state_store,
                 )?;
                 let instance = Self::instantiate_component(&runtime, &prepared, &mut store)?;
 
@@ -857,6 +967,7 @@ impl WasmChannel {
 
         match result {
             Ok(Ok((config, mut host_state))) => {
+                Self::queue_callbacks(&self.callback_tx, host_state.take_scheduled_callbacks());
                 // Surface WASM guest logs (errors/warnings from webhook setup, etc.)
                 for entry in host_state.take_logs() {
                     match entry.level {
@@ -943,6 +1054,7 @@ impl WasmChannel {
         let timeout = self.runtime.config().callback_timeout;
         let credentials = self.get_credentials().await;
         let pairing_store = self.pairing_store.clone();
+        let state_store = self.state_store.clone();
         let workspace_store = self.workspace_store.clone();
 
         // Prepare request data
@@ -963,6 +1075,7 @@ impl WasmChannel {
                     &capabilities,
                     credentials,
                     pairing_store,
+                    state_store,
                 )?;
                 let instance = Self::instantiate_component(&runtime, &prepared, &mut store)?;
 
@@ -1003,6 +1116,8 @@ impl WasmChannel {
         let channel_name = self.name.clone();
         match result {
             Ok(Ok((response, mut host_state))) => {
+                Self::queue_callbacks(&self.callback_tx, host_state.take_scheduled_callbacks());
+
                 // Process emitted messages
                 let emitted = host_state.take_emitted_messages();
                 self.process_emitted_messages(emitted).await?;
@@ -1042,6 +1157,7 @@ impl WasmChannel {
         let channel_name = self.name.clone();
         let credentials = self.get_credentials().await;
         let pairing_store = self.pairing_store.clone();
+        let state_store = self.state_store.clone();
         let workspace_store = self.workspace_store.clone();
 
         // Execute in blocking task with timeout
@@ -1053,6 +1169,7 @@ impl WasmChannel {
                     &capabilities,
                     credentials,
                     pairing_store,
+                    state_store,
                 )?;
                 let instance = Self::instantiate_component(&runtime, &prepared, &mut store)?;
 
@@ -1082,6 +1199,8 @@ impl WasmChannel {
         let channel_name = self.name.clone();
         match result {
             Ok(Ok(((), mut host_state))) => {
+                Self::queue_callbacks(&self.callback_tx, host_state.take_scheduled_callbacks());
+
                 // Process emitted messages
                 let emitted = host_state.take_emitted_messages();
                 self.process_emitted_messages(emitted).await?;
@@ -1143,6 +1262,7 @@ impl WasmChannel {
         let channel_name = self.name.clone();
         let credentials = self.get_credentials().await;
         let pairing_store = self.pairing_store.clone();
+        let state_store = self.state_store.clone();
 
         // Prepare response data
         let message_id_str = message_id.to_string();
@@ -1162,6 +1282,7 @@ impl WasmChannel {
                     &capabilities,
                     credentials,
                     pairing_store,
+                    state_store,
                 )?;
 
                 tracing::info!("Instantiating WASM component for on_respond");
@@ -1220,7 +1341,8 @@ impl WasmChannel {
 
         let channel_name = self.name.clone();
         match result {
-            Ok(Ok(((), _host_state))) => {
+            Ok(Ok(((), mut host_state))) => {
+                Self::queue_callbacks(&self.callback_tx, host_state.take_scheduled_callbacks());
                 tracing::debug!(
                     channel = %channel_name,
                     message_id = %message_id,
@@ -1256,6 +1378,7 @@ impl WasmChannel {
         let channel_name = self.name.clone();
         let credentials = self.get_credentials().await;
         let pairing_store = self.pairing_store.clone();
+        let state_store = self.state_store.clone();
 
         let wit_update = status_to_wit(status, metadata);
 
@@ -1267,6 +1390,7 @@ impl WasmChannel {
                     &capabilities,
                     credentials,
                     pairing_store,
+                    state_store,
                 )?;
                 let instance = Self::instantiate_component(&runtime, &prepared, &mut store)?;
 
@@ -1275,7 +1399,7 @@ impl WasmChannel {
                     .call_on_status(&mut store, &wit_update)
                     .map_err(|e| Self::map_wasm_error(e, &prepared.name, prepared.limits.fuel))?;
 
-                Ok(())
+                Ok(store.data_mut().host_state.take_scheduled_callbacks())
             })
             .await
             .map_err(|e| WasmChannelError::ExecutionPanicked {
@@ -1286,7 +1410,8 @@ impl WasmChannel {
         .await;
 
         match result {
-            Ok(Ok(())) => {
+            Ok(Ok(callbacks)) => {
+                Self::queue_callbacks(&self.callback_tx, callbacks);
                 tracing::debug!(
                     channel = %self.name,
                     "WASM channel on_status completed"
@@ -1301,6 +1426,80 @@ impl WasmChannel {
         }
     }
 
+    /// Execute the on_approval callback.
+    ///
+    /// Asks the channel to render an approval request with interactive
+    /// buttons. Returns `CallbackFailed` when the channel does not support
+    /// it, so the caller can fall back to a plain-text prompt.
+    pub async fn call_on_approval(
+        &self,
+        request: wit_channel::ApprovalRequest,
+    ) -> Result<(), WasmChannelError> {
+        // If no WASM bytes, do nothing (for testing)
+        if self.prepared.component().is_none() {
+            return Ok(());
+        }
+
+        let runtime = Arc::clone(&self.runtime);
+        let prepared = Arc::clone(&self.prepared);
+        let capabilities = self.capabilities.clone();
+        let timeout = self.runtime.config().callback_timeout;
+        let channel_name = self.name.clone();
+        let credentials = self.get_credentials().await;
+        let pairing_store = self.pairing_store.clone();
+        let state_store = self.state_store.clone();
+
+        let result = tokio::time::timeout(timeout, async move {
+            tokio::task::spawn_blocking(move || {
+                let mut store = Self::create_store(
+                    &runtime,
+                    &prepared,
+                    &capabilities,
+                    credentials,
+                    pairing_store,
+                    state_store,
+                )?;
+                let instance = Self::instantiate_component(&runtime, &prepared, &mut store)?;
+
+                let channel_iface = instance.near_agent_channel();
+                let wasm_result = channel_iface
+                    .call_on_approval(&mut store, &request)
+                    .map_err(|e| Self::map_wasm_error(e, &prepared.name, prepared.limits.fuel))?;
+
+                if let Err(reason) = wasm_result {
+                    return Err(WasmChannelError::CallbackFailed {
+                        name: prepared.name.clone(),
+                        reason,
+                    });
+                }
+
+                Ok(store.data_mut().host_state.take_scheduled_callbacks())
+            })
+            .await
+            .map_err(|e| WasmChannelError::ExecutionPanicked {
+                name: channel_name.clone(),
+                reason: e.to_string(),
+            })?
+        })
+        .await;
+
+        match result {
+            Ok(Ok(callbacks)) => {
+                Self::queue_callbacks(&self.callback_tx, callbacks);
+                tracing::debug!(
+                    channel = %self.name,
+                    "WASM channel on_approval completed"
+                );
+                Ok(())
+            }
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(WasmChannelError::Timeout {
+                name: self.name.clone(),
+                callback: "on_approval".to_string(),
+            }),
+        }
+    }
+
     /// Execute a single on_status callback with a fresh WASM instance.
     ///
     /// Static method for use by the background typing repeat task (which
@@ -1313,6 +1512,8 @@ impl WasmChannel {
         capabilities: &ChannelCapabilities,
         credentials: &RwLock<HashMap<String, String>>,
         pairing_store: Arc<PairingStore>,
+        state_store: Arc<ChannelStateStore>,
+        callback_tx: &mpsc::UnboundedSender<ScheduledCallback>,
         timeout: Duration,
         wit_update: wit_channel::StatusUpdate,
     ) -> Result<(), WasmChannelError> {
@@ -1334,6 +1535,7 @@ impl WasmChannel {
                     &capabilities,
                     credentials_snapshot,
                     pairing_store,
+                    state_store,
                 )?;
                 let instance = Self::instantiate_component(&runtime, &prepared, &mut store)?;
 
@@ -1342,7 +1544,7 @@ impl WasmChannel {
                     .call_on_status(&mut store, &wit_update)
                     .map_err(|e| Self::map_wasm_error(e, &prepared.name, prepared.limits.fuel))?;
 
-                Ok(())
+                Ok(store.data_mut().host_state.take_scheduled_callbacks())
             })
             .await
             .map_err(|e| WasmChannelError::ExecutionPanicked {
@@ -1353,7 +1555,10 @@ impl WasmChannel {
         .await;
 
         match result {
-            Ok(Ok(())) => Ok(()),
+            Ok(Ok(callbacks)) => {
+                Self::queue_callbacks(callback_tx, callbacks);
+                Ok(())
+            }
             Ok(Err(e)) => Err(e),
             Err(_) => Err(WasmChannelError::Timeout {
                 name: channel_name.to_string(),
@@ -1415,6 +1620,8 @@ impl WasmChannel {
                 let capabilities = self.capabilities.clone();
                 let credentials = self.credentials.clone();
                 let pairing_store = self.pairing_store.clone();
+                let state_store = self.state_store.clone();
+                let callback_tx = self.callback_tx.clone();
                 let callback_timeout = self.runtime.config().callback_timeout;
                 let wit_update = status_to_wit(&status, metadata);
 
@@ -1435,6 +1642,8 @@ impl WasmChannel {
                             &capabilities,
                             &credentials,
                             pairing_store.clone(),
+                            state_store.clone(),
+                            &callback_tx,
                             callback_timeout,
                             wit_update_clone,
                         )
@@ -1455,57 +1664,31 @@ impl WasmChannel {
                 // No-op, too noisy
             }
             StatusUpdate::ApprovalNeeded {
+                request_id,
                 tool_name,
                 description,
                 parameters,
-                ..
             } => {
-                // WASM channels (Telegram, Slack, etc.) cannot render
-                // interactive approval overlays.  Send the approval prompt
-                // as an actual message so the user can reply yes/no.
+                // Channels with buttons (Telegram inline keyboards, Slack
+                // block actions) render the request via on_approval. Others
+                // get the prompt as an actual message so the user can reply
+                // yes/no.
                 self.cancel_typing_task().await;
 
-                let params_preview = parameters
-                    .as_object()
-                    .map(|obj| {
-                        obj.iter()
-                            .map(|(k, v)| {
-                                let val = match v {
-                                    serde_json::Value::String(s) => {
-                                        if s.chars().count() > 80 {
-                                            let truncated: String = s.chars().take(77).collect();
-                                            format!("\"{}...\"", truncated)
-                                        } else {
-                                            format!("\"{}\"", s)
-                                        }
-                                    }
-                                    other => {
-                                        let s = other.to_string();
-                                        if s.chars().count() > 80 {
-                                            let truncated: String = s.chars().take(77).collect();
-                                            format!("{}...", truncated)
-                                        } else {
-                                            s
-                                        }
-                                    }
-                                };
-                                format!("  {}: {}", k, val)
-                            })
-                            .collect::<Vec<_>>()
-                            .join("\n")
-                    })
-                    .unwrap_or_default();
-
-                let prompt = format!(
-                    "Approval needed: {tool_name}\n\
-                     {description}\n\
-                     \n\
-                     Parameters:\n\
-                     {params_preview}\n\
-                     \n\
-                     Reply \"yes\" to approve, \"no\" to deny, or \"always\" to auto-approve."
-                );
+                let request =
+                    approval_to_wit(request_id, tool_name, description, parameters, metadata);
+                match self.call_on_approval(request).await {
+                    Ok(()) => return Ok(()),
+                    Err(e) => {
+                        tracing::debug!(
+                            channel = %self.name,
+                            error = %e,
+                            "on_approval unavailable, sending text approval prompt"
+                        );
+                    }
+                }
 
+                let prompt = approval_prompt_text(tool_name, description, parameters);
                 let metadata_json = serde_json::to_string(metadata).unwrap_or_default();
                 if let Err(e) = self
                     .call_on_respond(uuid::Uuid::new_v4(), &prompt, None, &metadata_json)
@@ -1654,8 +1837,10 @@ impl WasmChannel {
         let rate_limiter = self.rate_limiter.clone();
         let credentials = self.credentials.clone();
         let pairing_store = self.pairing_store.clone();
+        let state_store = self.state_store.clone();
         let callback_timeout = self.runtime.config().callback_timeout;
         let workspace_store = self.workspace_store.clone();
+        let callback_tx = self.callback_tx.clone();
 
         tokio::spawn(async move {
             let mut interval_timer = tokio::time::interval(interval);
@@ -1677,8 +1862,10 @@ impl WasmChannel {
                             &capabilities,
                             &credentials,
                             pairing_store.clone(),
+                            state_store.clone(),
                             callback_timeout,
                             &workspace_store,
+                            &callback_tx,
                         ).await;
 
                         match result {
@@ -1732,8 +1919,10 @@ impl WasmChannel {
         capabilities: &ChannelCapabilities,
         credentials: &RwLock<HashMap<String, String>>,
         pairing_store: Arc<PairingStore>,
+        state_store: Arc<ChannelStateStore>,
         timeout: Duration,
         workspace_store: &Arc<ChannelWorkspaceStore>,
+        callback_tx: &mpsc::UnboundedSender<ScheduledCallback>,
     ) -> Result<Vec<EmittedMessage>, WasmChannelError> {
         // Skip if no WASM bytes (testing mode)
         if prepared.component().is_none() {
@@ -1760,6 +1949,7 @@ impl WasmChannel {
                     &capabilities,
                     credentials_snapshot,
                     pairing_store,
+                    state_store,
                 )?;
                 let instance = Self::instantiate_component(&runtime, &prepared, &mut store)?;
 
@@ -1788,6 +1978,7 @@ impl WasmChannel {
 
         match result {
             Ok(Ok(mut host_state)) => {
+                Self::queue_callbacks(callback_tx, host_state.take_scheduled_callbacks());
                 let emitted = host_state.take_emitted_messages();
                 tracing::debug!(
                     channel = %channel_name,
@@ -1804,6 +1995,198 @@ impl WasmChannel {
         }
     }
 
+    /// Hand callbacks scheduled during an execution to the timer loop.
+    fn queue_callbacks(
+        callback_tx: &mpsc::UnboundedSender<ScheduledCallback>,
+        callbacks: Vec<ScheduledCallback>,
+    ) {
+        for callback in callbacks {
+            // The channel owns the receiver, so this only fails mid-drop.
+            let _ = callback_tx.send(callback);
+        }
+    }
+
+    /// Start the loop that fires callbacks requested via `schedule-callback`.
+    ///
+    /// Each queued callback gets its own timer; when one fires, `on_callback`
+    /// runs with a fresh WASM instance, just like a poll tick. Timers still
+    /// pending at shutdown are dropped.
+    fn start_callback_timer(&self, shutdown_rx: oneshot::Receiver<()>) {
+        let channel_name = self.name.clone();
+        let runtime = Arc::clone(&self.runtime);
+        let prepared = Arc::clone(&self.prepared);
+        let capabilities = self.capabilities.clone();
+        let message_tx = self.message_tx.clone();
+        let rate_limiter = self.rate_limiter.clone();
+        let credentials = self.credentials.clone();
+        let pairing_store = self.pairing_store.clone();
+        let state_store = self.state_store.clone();
+        let callback_timeout = self.runtime.config().callback_timeout;
+        let workspace_store = self.workspace_store.clone();
+        let callback_tx = self.callback_tx.clone();
+        let callback_rx = Arc::clone(&self.callback_rx);
+
+        tokio::spawn(async move {
+            let mut callback_rx = callback_rx.lock().await;
+            let mut timers = tokio::task::JoinSet::new();
+            let mut shutdown = std::pin::pin!(shutdown_rx);
+
+            loop {
+                tokio::select! {
+                    Some(callback) = callback_rx.recv() => {
+                        if timers.len() >= MAX_PENDING_CALLBACKS {
+                            tracing::warn!(
+                                channel = %channel_name,
+                                limit = MAX_PENDING_CALLBACKS,
+                                "Too many pending callbacks, dropping scheduled callback"
+                            );
+                            continue;
+                        }
+                        timers.spawn(async move {
+                            tokio::time::sleep(callback.delay).await;
+                            callback.payload
+                        });
+                    }
+                    Some(Ok(payload)) = timers.join_next() => {
+                        let result = Self::execute_scheduled_callback(
+                            &channel_name,
+                            &runtime,
+                            &prepared,
+                            &capabilities,
+                            &credentials,
+                            pairing_store.clone(),
+                            state_store.clone(),
+                            callback_timeout,
+                            &workspace_store,
+                            &callback_tx,
+                            payload,
+                        ).await;
+
+                        match result {
+                            Ok(emitted_messages) => {
+                                if !emitted_messages.is_empty()
+                                    && let Err(e) = Self::dispatch_emitted_messages(
+                                        &channel_name,
+                                        emitted_messages,
+                                        &message_tx,
+                                        &rate_limiter,
+                                    ).await {
+                                        tracing::warn!(
+                                            channel = %channel_name,
+                                            error = %e,
+                                            "Failed to dispatch emitted messages from callback"
+                                        );
+                                    }
+                            }
+                            Err(e) => {
+                                tracing::warn!(
+                                    channel = %channel_name,
+                                    error = %e,
+                                    "Scheduled callback failed"
+                                );
+                            }
+                        }
+                    }
+                    _ = &mut shutdown => {
+                        tracing::debug!(
+                            channel = %channel_name,
+                            pending = timers.len(),
+                            "Callback timer stopped"
+                        );
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Execute a single scheduled callback with a fresh WASM instance.
+    ///
+    /// Returns any emitted messages. Workspace writes are committed and any
+    /// callbacks the guest schedules in turn are queued on `callback_tx`.
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_scheduled_callback(
+        channel_name: &str,
+        runtime: &Arc<WasmChannelRuntime>,
+        prepared: &Arc<PreparedChannelModule>,
+        capabilities: &ChannelCapabilities,
+        credentials: &RwLock<HashMap<String, String>>,
+        pairing_store: Arc<PairingStore>,
+        state_store: Arc<ChannelStateStore>,
+        timeout: Duration,
+        workspace_store: &Arc<ChannelWorkspaceStore>,
+        callback_tx: &mpsc::UnboundedSender<ScheduledCallback>,
+        payload: String,
+    ) -> Result<Vec<EmittedMessage>, WasmChannelError> {
+        // Skip if no WASM bytes (testing mode)
+        if prepared.component().is_none() {
+            tracing::debug!(
+                channel = %channel_name,
+                "WASM channel on_callback called (no WASM module)"
+            );
+            return Ok(Vec::new());
+        }
+
+        let runtime = Arc::clone(runtime);
+        let prepared = Arc::clone(prepared);
+        let capabilities = Self::inject_workspace_reader(capabilities, workspace_store);
+        let credentials_snapshot = credentials.read().await.clone();
+        let channel_name_owned = channel_name.to_string();
+        let workspace_store = Arc::clone(workspace_store);
+
+        let result = tokio::time::timeout(timeout, async move {
+            tokio::task::spawn_blocking(move || {
+                let mut store = Self::create_store(
+                    &runtime,
+                    &prepared,
+                    &capabilities,
+                    credentials_snapshot,
+                    pairing_store,
+                    state_store,
+                )?;
+                let instance = Self::instantiate_component(&runtime, &prepared, &mut store)?;
+
+                let channel_iface = instance.near_agent_channel();
+                channel_iface
+                    .call_on_callback(&mut store, &payload)
+                    .map_err(|e| Self::map_wasm_error(e, &prepared.name, prepared.limits.fuel))?;
+
+                let mut host_state =
+                    Self::extract_host_state(&mut store, &prepared.name, &capabilities);
+
+                // Commit pending workspace writes to the persistent store
+                let pending_writes = host_state.take_pending_writes();
+                workspace_store.commit_writes(&pending_writes);
+
+                Ok(host_state)
+            })
+            .await
+            .map_err(|e| WasmChannelError::ExecutionPanicked {
+                name: channel_name_owned.clone(),
+                reason: e.to_string(),
+            })?
+        })
+        .await;
+
+        match result {
+            Ok(Ok(mut host_state)) => {
+                Self::queue_callbacks(callback_tx, host_state.take_scheduled_callbacks());
+                let emitted = host_state.take_emitted_messages();
+                tracing::debug!(
+                    channel = %channel_name,
+                    emitted_count = emitted.len(),
+                    "WASM channel on_callback completed"
+                );
+                Ok(emitted)
+            }
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(WasmChannelError::Timeout {
+                name: channel_name.to_string(),
+                callback: "on_callback".to_string(),
+            }),
+        }
+    }
+
     /// Dispatch emitted messages to the message channel.
     ///
     /// This is a static helper used by the polling loop since it doesn't have
@@ -1954,6 +2337,12 @@ impl Channel for WasmChannel {
             self.start_polling(Duration::from_millis(interval as u64), poll_shutdown_rx);
         }
 
+        // Fire callbacks requested via schedule-callback (including any
+        // queued by on_start above)
+        let (callback_shutdown_tx, callback_shutdown_rx) = oneshot::channel();
+        *self.callback_shutdown_tx.write().await = Some(callback_shutdown_tx);
+        self.start_callback_timer(callback_shutdown_rx);
+
         tracing::info!(
             channel = %self.name,
             display_name = %config.display_name,
@@ -2029,6 +2418,9 @@ impl Channel for WasmChannel {
         // Stop polling by dropping the sender (receiver will complete)
         let _ = self.poll_shutdown_tx.write().await.take();
 
+        // Stop the callback timer the same way
+        let _ = self.callback_shutdown_tx.write().await.take();
+
         // Clear the message sender
         *self.message_tx.write().await = None;
 
@@ -2288,6 +2680,71 @@ fn status_to_wit(status: &StatusUpdate, metadata: &serde_json::Value) -> wit_cha
     }
 }
 
+/// Build the WIT approval request handed to on_approval.
+fn approval_to_wit(
+    request_id: &str,
+    tool_name: &str,
+    description: &str,
+    parameters: &serde_json::Value,
+    metadata: &serde_json::Value,
+) -> wit_channel::ApprovalRequest {
+    wit_channel::ApprovalRequest {
+        request_id: request_id.to_string(),
+        tool_name: tool_name.to_string(),
+        description: description.to_string(),
+        parameters_json: serde_json::to_string(parameters).unwrap_or_default(),
+        metadata_json: serde_json::to_string(metadata).unwrap_or_default(),
+    }
+}
+
+/// Plain-text approval prompt for channels without interactive buttons.
+fn approval_prompt_text(
+    tool_name: &str,
+    description: &str,
+    parameters: &serde_json::Value,
+) -> String {
+    let params_preview = parameters
+        .as_object()
+        .map(|obj| {
+            obj.iter()
+                .map(|(k, v)| {
+                    let val = match v {
+                        serde_json::Value::String(s) => {
+                            if s.chars().count() > 80 {
+                                let truncated: String = s.chars().take(77).collect();
+                                format!("\"{}...\"", truncated)
+                            } else {
+                                format!("\"{}\"", s)
+                            }
+                        }
+                        other => {
+                            let s = other.to_string();
+                            if s.chars().count() > 80 {
+                                let truncated: String = s.chars().take(77).collect();
+                                format!("{}...", truncated)
+                            } else {
+                                s
+                            }
+                        }
+                    };
+                    format!("  {}: {}", k, val)
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default();
+
+    format!(
+        "Approval needed: {tool_name}\n\
+         {description}\n\
+         \n\
+         Parameters:\n\
+         {params_preview}\n\
+         \n\
+         Reply \"yes\" to approve, \"no\" to deny, or \"always\" to auto-approve."
+    )
+}
+
 /// Clone a WIT StatusUpdate (the generated type doesn't derive Clone).
 fn clone_wit_status_update(update: &wit_channel::StatusUpdate) -> wit_channel::StatusUpdate {
     wit_channel::StatusUpdate {
@@ -2358,6 +2815,7 @@ mod tests {
 
     use crate::channels::Channel;
     use crate::channels::wasm::capabilities::ChannelCapabilities;
+    use crate::channels::wasm::host::ChannelStateStore;
     use crate::channels::wasm::runtime::{
         PreparedChannelModule, WasmChannelRuntime, WasmChannelRuntimeConfig,
     };
@@ -2435,6 +2893,78 @@ mod tests {
         assert!(channel.health_check().await.is_err());
     }
 
+    #[tokio::test]
+    async fn test_callback_timer_releases_queue_on_shutdown() {
+        use crate::channels::wasm::host::ScheduledCallback;
+
+        let channel = create_test_channel();
+        WasmChannel::queue_callbacks(
+            &channel.callback_tx,
+            vec![ScheduledCallback {
+                delay: std::time::Duration::ZERO,
+                payload: "queued-before-start".to_string(),
+            }],
+        );
+
+        let _stream = channel.start().await.unwrap();
+
+        // The timer loop holds the queue while it runs.
+        let mut running = false;
+        for _ in 0..100 {
+            if channel.callback_rx.try_lock().is_err() {
+                running = true;
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(running, "callback timer never started");
+
+        channel.shutdown().await.unwrap();
+
+        // After shutdown the queue is released so a restart can pick it up.
+        let mut released = false;
+        for _ in 0..100 {
+            if channel.callback_rx.try_lock().is_ok() {
+                released = true;
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(released, "callback timer did not stop on shutdown");
+    }
+
+    #[tokio::test]
+    async fn test_execute_scheduled_callback_no_wasm_returns_empty() {
+        let config = WasmChannelRuntimeConfig::for_testing();
+        let runtime = Arc::new(WasmChannelRuntime::new(config).unwrap());
+        let prepared = Arc::new(PreparedChannelModule {
+            name: "callback-test".to_string(),
+            description: "Test channel".to_string(),
+            component: None,
+            limits: ResourceLimits::default(),
+        });
+        let capabilities = ChannelCapabilities::for_channel("callback-test");
+        let credentials = tokio::sync::RwLock::new(std::collections::HashMap::new());
+        let workspace_store = Arc::new(crate::channels::wasm::host::ChannelWorkspaceStore::new());
+
+        let emitted = WasmChannel::execute_scheduled_callback(
+            "callback-test",
+            &runtime,
+            &prepared,
+            &capabilities,
+            &credentials,
+            Arc::new(PairingStore::new()),
+            Arc::new(ChannelStateStore::in_memory("callback-test")),
+            std::time::Duration::from_secs(5),
+            &workspace_store,
+            &tokio::sync::mpsc::unbounded_channel().0,
+            "retry:42".to_string(),
+        )
+        .await
+        .unwrap();
+        assert!(emitted.is_empty());
+    }
+
     #[tokio::test]
     async fn test_execute_poll_no_wasm_returns_empty() {
         // When there's no WASM module (None component), execute_poll
@@ -2462,8 +2992,12 @@ mod tests {
             &capabilities,
             &credentials,
             Arc::new(PairingStore::new()),
+            Arc::new(crate::channels::wasm::host::ChannelStateStore::in_memory(
+                "poll-test",
+            )),
             timeout,
             &workspace_store,
+            &tokio::sync::mpsc::unbounded_channel().0,
         )
         .await;
 
@@ -3205,6 +3739,60 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_approval_to_wit_carries_request_and_routing() {
+        use super::approval_to_wit;
+        use crate::agent::submission::{Submission, SubmissionParser};
+
+        let request_id = uuid::Uuid::new_v4();
+        let wit = approval_to_wit(
+            &request_id.to_string(),
+            "send_email",
+            "Send engagement letter",
+            &serde_json::json!({"to": "client@example.com"}),
+            &serde_json::json!({"chat_id": 42, "message_id": 7}),
+        );
+
+        assert_eq!(wit.request_id, request_id.to_string());
+        assert_eq!(wit.tool_name, "send_email");
+        let params: serde_json::Value = serde_json::from_str(&wit.parameters_json).unwrap();
+        assert_eq!(params["to"], "client@example.com");
+        let metadata: serde_json::Value = serde_json::from_str(&wit.metadata_json).unwrap();
+        assert_eq!(metadata["chat_id"], 42);
+
+        // The content channels emit when a button is pressed.
+        let pressed = serde_json::json!({
+            "ExecApproval": {
+                "request_id": wit.request_id,
+                "approved": true,
+                "always": false,
+            }
+        })
+        .to_string();
+        assert!(matches!(
+            SubmissionParser::parse(&pressed),
+            Submission::ExecApproval { request_id: rid, approved: true, always: false }
+                if rid == request_id
+        ));
+    }
+
+    #[test]
+    fn test_approval_prompt_text_truncates_long_parameters() {
+        use super::approval_prompt_text;
+
+        let long = "x".repeat(200);
+        let prompt = approval_prompt_text(
+            "http_request",
+            "Fetch a page",
+            &serde_json::json!({"url": long, "timeout": 30}),
+        );
+
+        assert!(prompt.starts_with("Approval needed: http_request\nFetch a page"));
+        assert!(prompt.contains(&format!("  url: \"{}...\"", "x".repeat(77))));
+        assert!(prompt.contains("  timeout: 30"));
+        assert!(prompt.contains("Reply \"yes\" to approve"));
+    }
+
     #[test]
     fn test_clone_wit_status_update() {
         use super::{clone_wit_status_update, wit_channel};
@@ -3310,6 +3898,7 @@ mod tests {
             ChannelCapabilities::default(),
             creds,
             Arc::new(PairingStore::new()),
+            Arc::new(ChannelStateStore::in_memory("test")),
         );
 
         let error = "HTTP request failed: error sending request for url \
@@ -3341,6 +3930,7 @@ mod tests {
             ChannelCapabilities::default(),
             std::collections::HashMap::new(),
             Arc::new(PairingStore::new()),
+            Arc::new(ChannelStateStore::in_memory("test")),
         );
 
         let input = "some error message";
@@ -3360,12 +3950,54 @@ mod tests {
             ChannelCapabilities::default(),
             creds,
             Arc::new(PairingStore::new()),
+            Arc::new(ChannelStateStore::in_memory("test")),
         );
 
         let input = "should not match anything";
         assert_eq!(store.redact_credentials(input), input);
     }
 
+    #[test]
+    fn test_state_host_functions_share_channel_store() {
+        use super::ChannelStoreData;
+        use super::near::agent::channel_host::Host;
+
+        let state = Arc::new(ChannelStateStore::in_memory("telegram"));
+        let mut store = ChannelStoreData::new(
+            1024 * 1024,
+            "telegram",
+            ChannelCapabilities::default(),
+            std::collections::HashMap::new(),
+            Arc::new(PairingStore::new()),
+            Arc::clone(&state),
+        );
+
+        store
+            .state_set("last_update_id".to_string(), "104".to_string())
+            .unwrap();
+        assert!(
+            store
+                .state_set("bad key".to_string(), String::new())
+                .is_err()
+        );
+
+        // A later callback gets a fresh store over the same channel state.
+        let mut next = ChannelStoreData::new(
+            1024 * 1024,
+            "telegram",
+            ChannelCapabilities::default(),
+            std::collections::HashMap::new(),
+            Arc::new(PairingStore::new()),
+            Arc::clone(&state),
+        );
+        assert_eq!(
+            next.state_get("last_update_id".to_string()),
+            Some("104".to_string())
+        );
+        assert_eq!(next.state_delete("last_update_id".to_string()), Ok(true));
+        assert_eq!(state.get("last_update_id"), None);
+    }
+
     /// Verify that WASM HTTP host functions work using a dedicated
     /// current-thread runtime inside spawn_blocking.
     #[tokio::test]
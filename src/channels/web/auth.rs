@@ -144,12 +144,23 @@ pub fn hash_auth_token(token: &str) -> String {
 /// re-upserts the token hash) automatically invalidates previously stored
 /// hashes, which is the desired behaviour.
 pub fn derive_token_hmac_key(auth_token: &str) -> Vec<u8> {
+    derive_key(auth_token, b"ironclaw-token-hash-v1")
+}
+
+/// Derive the HMAC key for precedent anonymization review tokens from the
+/// gateway auth token, under its own HKDF label so it never equals the
+/// token-hash key.
+pub fn derive_review_token_key(auth_token: &str) -> Vec<u8> {
+    derive_key(auth_token, b"clawyer-review-token-v1")
+}
+
+fn derive_key(auth_token: &str, info: &[u8]) -> Vec<u8> {
     let hk = Hkdf::<Sha256>::new(None, auth_token.as_bytes());
     let mut okm = vec![0u8; 32];
     // HKDF-SHA256 can produce up to 255 * 32 = 8 160 bytes of output.
     // Requesting 32 bytes is always within this limit; the error branch is
     // unreachable in practice. Degrade to raw SHA-256 bytes if it ever fires.
-    if hk.expand(b"ironclaw-token-hash-v1", &mut okm).is_err() {
+    if hk.expand(info, &mut okm).is_err() {
         let mut hasher = Sha256::new();
         Digest::update(&mut hasher, auth_token.as_bytes());
         return hasher.finalize().to_vec();
@@ -12,9 +12,10 @@ use axum::{
 use chrono::Utc;
 use uuid::Uuid;
 
+use crate::channels::web::auth::{AuthPrincipal, RequestPrincipal};
 use crate::channels::web::state::GatewayState;
 use crate::channels::web::types::*;
-use crate::db::AuditSeverity;
+use crate::db::{AuditSeverity, UserRole};
 
 pub fn routes() -> Router<Arc<GatewayState>> {
     Router::new()
@@ -27,6 +28,7 @@ pub fn routes() -> Router<Arc<GatewayState>> {
             )),
         )
         .route("/api/backups/{id}/download", get(backups_download_handler))
+        .route("/api/system/backups", get(backups_list_handler))
 }
 
 fn parse_multipart_bool(raw: &str) -> bool {
@@ -36,6 +38,18 @@ fn parse_multipart_bool(raw: &str) -> bool {
     )
 }
 
+/// Backup artifacts hold the whole firm's data, so only administrators may
+/// see them.
+fn require_admin(principal: &AuthPrincipal) -> Result<(), (StatusCode, String)> {
+    if principal.role != UserRole::Admin {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only administrators can list backups".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 async fn resolve_backup_master_key() -> Result<secrecy::SecretString, (StatusCode, String)> {
     let secrets = crate::config::SecretsConfig::resolve()
         .await
@@ -66,11 +80,7 @@ pub(crate) async fn backups_create_handler(
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    let backup_id = format!(
-        "backup-{}-{}",
-        Utc::now().format("%Y%m%d-%H%M%S"),
-        Uuid::new_v4().simple()
-    );
+    let backup_id = crate::legal::backup_schedule::new_backup_id();
     let output_path = backup_dir.join(format!("{backup_id}.clawyerbak"));
     let legal = crate::channels::web::server::legal_config_for_gateway_or_500(state.as_ref())?;
 
@@ -120,6 +130,30 @@ pub(crate) async fn backups_create_handler(
     ))
 }
 
+pub(crate) async fn backups_list_handler(
+    State(_state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+) -> Result<Json<BackupListResponse>, (StatusCode, String)> {
+    require_admin(&principal)?;
+    let backup_dir = crate::legal::backup::backups_dir()
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let listings = crate::legal::backup_schedule::list_backups(&backup_dir)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(BackupListResponse {
+        backups: listings
+            .into_iter()
+            .map(|listing| BackupListEntry {
+                id: listing.id,
+                created_at: listing.created_at,
+                size_bytes: listing.size_bytes,
+                user_id: listing.user_id,
+                app_version: listing.app_version,
+            })
+            .collect(),
+    }))
+}
+
 pub(crate) async fn backups_verify_handler(
     State(state): State<Arc<GatewayState>>,
     Json(req): Json<BackupVerifyRequest>,
@@ -1,20 +1,35 @@
 //! Chat handlers.
 
+use std::convert::Infallible;
 use std::sync::Arc;
 
 use axum::{
     Json, Router,
-    extract::{Query, State, WebSocketUpgrade},
+    extract::{Path, Query, State, WebSocketUpgrade},
     http::StatusCode,
-    response::IntoResponse,
+    response::{
+        IntoResponse, Response,
+        sse::{Event, Sse},
+    },
     routing::{get, post},
 };
+use futures::StreamExt;
 use serde_json::json;
 use uuid::Uuid;
 
+use crate::agent::thread_config::{
+    MAX_ENABLED_TOOLS, MAX_SYSTEM_PROMPT_ADDITIONS_CHARS, THREAD_CONFIG_METADATA_KEY, ThreadConfig,
+    ThreadTone,
+};
 use crate::channels::IncomingMessage;
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::handlers::helpers::intake::intake_error_response;
+use crate::channels::web::handlers::helpers::matter::require_matter_access;
+use crate::channels::web::sse::{SseTopics, keep_alive};
 use crate::channels::web::state::GatewayState;
 use crate::channels::web::types::*;
+use crate::db::{AuditSeverity, MatterMemberRole};
+use crate::legal::memo::{ConversationMemo, MemoExchange, default_memo_title};
 
 pub fn routes() -> Router<Arc<GatewayState>> {
     Router::new()
@@ -27,19 +42,24 @@ pub fn routes() -> Router<Arc<GatewayState>> {
         .route("/api/chat/history", get(chat_history_handler))
         .route("/api/chat/threads", get(chat_threads_handler))
         .route("/api/chat/thread/new", post(chat_new_thread_handler))
+        .route(
+            "/api/chat/threads/{thread_id}/file-to-matter",
+            post(chat_file_to_matter_handler),
+        )
+        .route(
+            "/api/chat/threads/{thread_id}/branch",
+            post(chat_branch_thread_handler),
+        )
+        .route(
+            "/api/chat/threads/{thread_id}/config",
+            get(chat_thread_config_handler).patch(chat_thread_config_patch_handler),
+        )
 }
 
 pub(crate) async fn chat_send_handler(
     State(state): State<Arc<GatewayState>>,
     Json(req): Json<SendMessageRequest>,
-) -> Result<(StatusCode, Json<SendMessageResponse>), (StatusCode, String)> {
-    if !state.chat_rate_limiter.check() {
-        return Err((
-            StatusCode::TOO_MANY_REQUESTS,
-            "Rate limit exceeded. Try again shortly.".to_string(),
-        ));
-    }
-
+) -> Result<(StatusCode, Json<SendMessageResponse>), Response> {
     let mut msg = IncomingMessage::new("gateway", &state.user_id, &req.content);
 
     if let Some(ref thread_id) = req.thread_id {
@@ -55,18 +75,10 @@ pub(crate) async fn chat_send_handler(
 
     let msg_id = msg.id;
 
-    let tx_guard = state.msg_tx.read().await;
-    let tx = tx_guard.as_ref().ok_or((
-        StatusCode::SERVICE_UNAVAILABLE,
-        "Channel not started".to_string(),
-    ))?;
-
-    tx.send(msg).await.map_err(|_| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Channel closed".to_string(),
-        )
-    })?;
+    state
+        .enqueue_message(msg)
+        .await
+        .map_err(intake_error_response)?;
 
     Ok((
         StatusCode::ACCEPTED,
@@ -80,7 +92,7 @@ pub(crate) async fn chat_send_handler(
 pub(crate) async fn chat_approval_handler(
     State(state): State<Arc<GatewayState>>,
     Json(req): Json<ApprovalRequest>,
-) -> Result<(StatusCode, Json<SendMessageResponse>), (StatusCode, String)> {
+) -> Result<(StatusCode, Json<SendMessageResponse>), Response> {
     let (approved, always) = match req.action.as_str() {
         "approve" => (true, false),
         "always" => (true, true),
@@ -89,7 +101,8 @@ pub(crate) async fn chat_approval_handler(
             return Err((
                 StatusCode::BAD_REQUEST,
                 format!("Unknown action: {}", other),
-            ));
+            )
+                .into_response());
         }
     };
 
@@ -98,6 +111,7 @@ pub(crate) async fn chat_approval_handler(
             StatusCode::BAD_REQUEST,
             "Invalid request_id (expected UUID)".to_string(),
         )
+            .into_response()
     })?;
 
     let approval = crate::agent::submission::Submission::ExecApproval {
@@ -110,6 +124,7 @@ pub(crate) async fn chat_approval_handler(
             StatusCode::INTERNAL_SERVER_ERROR,
             format!("Failed to serialize approval: {}", e),
         )
+            .into_response()
     })?;
 
     let mut msg = IncomingMessage::new("gateway", &state.user_id, content);
@@ -127,18 +142,10 @@ pub(crate) async fn chat_approval_handler(
 
     let msg_id = msg.id;
 
-    let tx_guard = state.msg_tx.read().await;
-    let tx = tx_guard.as_ref().ok_or((
-        StatusCode::SERVICE_UNAVAILABLE,
-        "Channel not started".to_string(),
-    ))?;
-
-    tx.send(msg).await.map_err(|_| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Channel closed".to_string(),
-        )
-    })?;
+    state
+        .enqueue_message(msg)
+        .await
+        .map_err(intake_error_response)?;
 
     Ok((
         StatusCode::ACCEPTED,
@@ -208,16 +215,58 @@ pub(crate) async fn chat_auth_cancel_handler(
     Ok(Json(ActionResponse::ok("Auth cancelled")))
 }
 
+#[derive(Debug, Default, serde::Deserialize)]
+pub(crate) struct ChatEventsQuery {
+    /// Comma-separated topics (`chat`, `jobs`, `matters`, `logs`); default
+    /// everything but logs.
+    pub(crate) topics: Option<String>,
+    /// Resume point for clients that cannot set `Last-Event-ID`.
+    pub(crate) last_event_id: Option<u64>,
+}
+
+/// Stream gateway events as SSE.
+///
+/// Events carry ids, so a reconnecting `EventSource` gets what it missed via
+/// `Last-Event-ID`; see [`crate::channels::web::sse`] for replay limits.
 pub(crate) async fn chat_events_handler(
     State(state): State<Arc<GatewayState>>,
+    Query(query): Query<ChatEventsQuery>,
+    headers: axum::http::HeaderMap,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    let sse = state.sse.subscribe().ok_or((
+    let topics = match query.topics.as_deref() {
+        Some(list) if !list.trim().is_empty() => {
+            SseTopics::parse(list).map_err(|e| (StatusCode::BAD_REQUEST, e))?
+        }
+        _ => SseTopics::default(),
+    };
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .or(query.last_event_id);
+
+    let events = state.sse.subscribe_events(last_event_id, topics).ok_or((
         StatusCode::SERVICE_UNAVAILABLE,
         "Too many connections".to_string(),
     ))?;
+    let logs = match (topics.logs, state.log_broadcaster.as_ref()) {
+        (true, Some(broadcaster)) => {
+            let rx = broadcaster.subscribe();
+            tokio_stream::wrappers::BroadcastStream::new(rx)
+                .filter_map(|entry| {
+                    futures::future::ready(entry.ok().map(|entry| {
+                        let data = serde_json::to_string(&entry).unwrap_or_default();
+                        Ok::<_, Infallible>(Event::default().event("log").data(data))
+                    }))
+                })
+                .boxed()
+        }
+        _ => futures::stream::empty().boxed(),
+    };
+
     Ok((
         [("X-Accel-Buffering", "no"), ("Cache-Control", "no-cache")],
-        sse,
+        Sse::new(futures::stream::select(events.boxed(), logs)).keep_alive(keep_alive()),
     ))
 }
 
@@ -225,6 +274,7 @@ pub(crate) async fn chat_ws_handler(
     headers: axum::http::HeaderMap,
     ws: WebSocketUpgrade,
     State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
     let origin = headers
         .get("origin")
@@ -249,7 +299,9 @@ pub(crate) async fn chat_ws_handler(
             "WebSocket origin not allowed".to_string(),
         ));
     }
-    Ok(ws.on_upgrade(move |socket| crate::channels::web::ws::handle_ws_connection(socket, state)))
+    Ok(ws.on_upgrade(move |socket| {
+        crate::channels::web::ws::handle_ws_connection(socket, state, principal.user_id)
+    }))
 }
 
 pub(crate) async fn chat_history_handler(
@@ -580,3 +632,459 @@ pub(crate) async fn chat_new_thread_handler(
 
     Ok(Json(info))
 }
+
+/// Turns of `thread_id`, from the live session when loaded, else from the
+/// conversation store.
+async fn load_thread_turns(
+    state: &GatewayState,
+    thread_id: Uuid,
+) -> Result<Vec<TurnInfo>, (StatusCode, String)> {
+    if let Some(session_manager) = state.session_manager.as_ref() {
+        let session = session_manager.get_or_create_session(&state.user_id).await;
+        let sess = session.lock().await;
+        if let Some(thread) = sess.threads.get(&thread_id) {
+            return Ok(crate::channels::web::server::build_turns_from_session_thread(thread));
+        }
+    }
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let owned = store
+        .conversation_belongs_to_user(thread_id, &state.user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !owned {
+        return Err((StatusCode::NOT_FOUND, "Thread not found".to_string()));
+    }
+    let messages = store
+        .list_conversation_messages(thread_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(crate::channels::web::server::build_turns_from_db_messages(
+        &messages,
+    ))
+}
+
+/// A thread created by [`branch_thread`].
+struct ThreadBranch {
+    id: Uuid,
+    source_id: Uuid,
+    turn: usize,
+    copied_turns: usize,
+    matter_id: Option<String>,
+    /// Question to ask again on the branch, when regenerating.
+    regenerate_input: Option<String>,
+}
+
+/// Create the branch conversation: copies of the source's turns up to and
+/// including `turn` (before it, when regenerating), the source's matter
+/// binding and thread configuration, and a `branched_from` marker.
+async fn branch_thread(
+    state: &GatewayState,
+    thread_id: &str,
+    req: &BranchThreadRequest,
+) -> Result<ThreadBranch, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let source_id = crate::channels::web::server::parse_uuid(thread_id, "thread_id")?;
+    let turns = load_thread_turns(state, source_id).await?;
+    let last = turns.last().map(|turn| turn.turn_number).ok_or((
+        StatusCode::BAD_REQUEST,
+        "Thread has no turns to branch from".to_string(),
+    ))?;
+    let turn_number = req.turn.unwrap_or(last);
+    let branch_turn = turns
+        .iter()
+        .find(|turn| turn.turn_number == turn_number)
+        .ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Turn {turn_number} does not exist (latest is {last})"),
+            )
+        })?;
+    let copied: Vec<&TurnInfo> = turns
+        .iter()
+        .filter(|turn| {
+            turn.turn_number < turn_number || (!req.regenerate && turn.turn_number == turn_number)
+        })
+        .collect();
+
+    let source_metadata = store
+        .get_conversation_metadata(source_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let mut metadata = json!({
+        "thread_type": "thread",
+        "branched_from": {
+            "thread_id": source_id.to_string(),
+            "turn": turn_number,
+        },
+    });
+    if let Some(config) = source_metadata
+        .as_ref()
+        .and_then(|m| m.get(THREAD_CONFIG_METADATA_KEY))
+        .filter(|v| !v.is_null())
+    {
+        metadata[THREAD_CONFIG_METADATA_KEY] = config.clone();
+    }
+    let id = store
+        .create_conversation_with_metadata("gateway", &state.user_id, &metadata)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    for turn in &copied {
+        let messages = std::iter::once(("user", turn.user_input.as_str()))
+            .chain(turn.response.as_deref().map(|r| ("assistant", r)));
+        for (role, content) in messages {
+            store
+                .add_conversation_message(id, role, content)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        }
+    }
+    let matter_id = store
+        .get_conversation_matter_id(source_id, &state.user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if let Some(matter_id) = matter_id.as_deref() {
+        store
+            .bind_conversation_to_matter(id, &state.user_id, matter_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    }
+
+    Ok(ThreadBranch {
+        id,
+        source_id,
+        turn: turn_number,
+        copied_turns: copied.len(),
+        matter_id,
+        regenerate_input: req.regenerate.then(|| branch_turn.user_input.clone()),
+    })
+}
+
+/// Start a new thread from an earlier point of a thread, leaving the
+/// original untouched. With `regenerate`, the question of the branch turn
+/// is sent again on the new thread for an alternative answer.
+pub(crate) async fn chat_branch_thread_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(thread_id): Path<String>,
+    Json(req): Json<BranchThreadRequest>,
+) -> Result<(StatusCode, Json<BranchThreadResponse>), Response> {
+    let branch = branch_thread(state.as_ref(), &thread_id, &req)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let mut message_id = None;
+    if let Some(content) = branch.regenerate_input {
+        let branch_thread_id = branch.id.to_string();
+        let msg = IncomingMessage::new("gateway", &state.user_id, content)
+            .with_thread(&branch_thread_id)
+            .with_metadata(
+                crate::channels::web::server::build_chat_message_metadata(
+                    state.as_ref(),
+                    Some(&branch_thread_id),
+                )
+                .await,
+            );
+        message_id = Some(msg.id);
+        state
+            .enqueue_message(msg)
+            .await
+            .map_err(intake_error_response)?;
+    }
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "chat_thread_branched",
+        &principal.user_id,
+        branch.matter_id.as_deref(),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "thread_id": branch.source_id.to_string(),
+            "branch_thread_id": branch.id.to_string(),
+            "turn": branch.turn,
+            "copied_turns": branch.copied_turns,
+            "regenerate": req.regenerate,
+        }),
+    )
+    .await;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    Ok((
+        StatusCode::CREATED,
+        Json(BranchThreadResponse {
+            thread: ThreadInfo {
+                id: branch.id,
+                state: "Idle".to_string(),
+                turn_count: branch.copied_turns,
+                created_at: now.clone(),
+                updated_at: now,
+                title: None,
+                matter_id: branch.matter_id,
+                thread_type: Some("thread".to_string()),
+            },
+            branched_from: branch.source_id,
+            turn: branch.turn,
+            copied_turns: branch.copied_turns,
+            message_id,
+        }),
+    ))
+}
+
+fn turn_to_exchange(turn: &TurnInfo) -> Option<MemoExchange> {
+    let answer = turn.response.as_ref()?;
+    Some(MemoExchange {
+        asked_at: chrono::DateTime::parse_from_rfc3339(&turn.started_at)
+            .ok()
+            .map(|dt| dt.with_timezone(&chrono::Utc)),
+        question: turn.user_input.clone(),
+        answer: answer.clone(),
+    })
+}
+
+/// File selected turns of a chat thread into a matter as a memo document.
+pub(crate) async fn chat_file_to_matter_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(thread_id): Path<String>,
+    Json(req): Json<FileChatToMatterRequest>,
+) -> Result<Json<FileChatToMatterResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let thread_id = crate::channels::web::server::parse_uuid(&thread_id, "thread_id")?;
+    let matter_root = crate::channels::web::server::matter_root_for_gateway(state.as_ref());
+    let matter_id = crate::channels::web::server::ensure_existing_matter_for_route(
+        workspace.as_ref(),
+        &matter_root,
+        &req.matter_id,
+    )
+    .await?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Collaborator,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    crate::channels::web::server::ensure_matter_db_row_from_workspace(state.as_ref(), &matter_id)
+        .await?;
+
+    let turns = load_thread_turns(state.as_ref(), thread_id).await?;
+    let exchanges = if req.turns.is_empty() {
+        let latest = turns.iter().rev().find_map(turn_to_exchange).ok_or((
+            StatusCode::BAD_REQUEST,
+            "Thread has no answered exchange to file".to_string(),
+        ))?;
+        vec![latest]
+    } else {
+        let mut selected = req.turns.clone();
+        selected.sort_unstable();
+        selected.dedup();
+        selected
+            .into_iter()
+            .map(|number| {
+                turns
+                    .iter()
+                    .find(|turn| turn.turn_number == number)
+                    .and_then(turn_to_exchange)
+                    .ok_or((
+                        StatusCode::BAD_REQUEST,
+                        format!("Turn {number} has no answered exchange to file"),
+                    ))
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    let filed_by = crate::channels::web::server::parse_optional_matter_field(req.attorney)
+        .unwrap_or_else(|| principal.user_id.clone());
+    let title = crate::channels::web::server::parse_optional_matter_field(req.title)
+        .unwrap_or_else(|| default_memo_title(&exchanges));
+    let memo = ConversationMemo {
+        matter_id: matter_id.clone(),
+        title,
+        filed_by: filed_by.clone(),
+        filed_at: chrono::Utc::now(),
+        thread_id,
+        model: state
+            .llm_provider
+            .as_ref()
+            .map(|llm| llm.active_model_name()),
+        exchanges,
+    };
+    let document = crate::legal::memo::file_conversation_memo(
+        store.as_ref(),
+        workspace.as_ref(),
+        &state.user_id,
+        &matter_root,
+        &memo,
+    )
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "chat_exchange_filed_to_matter",
+        filed_by.as_str(),
+        Some(matter_id.as_str()),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "thread_id": thread_id.to_string(),
+            "turns": memo.exchanges.len(),
+            "path": document.path,
+        }),
+    )
+    .await;
+    Ok(Json(FileChatToMatterResponse {
+        matter_id,
+        document: crate::channels::web::server::matter_document_record_to_info(document),
+    }))
+}
+
+/// Stored configuration of an owned thread; 404 for threads the user does
+/// not own.
+async fn load_owned_thread_config(
+    state: &GatewayState,
+    thread_id: Uuid,
+) -> Result<ThreadConfig, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let owned = store
+        .conversation_belongs_to_user(thread_id, &state.user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !owned {
+        return Err((StatusCode::NOT_FOUND, "Thread not found".to_string()));
+    }
+    let metadata = store
+        .get_conversation_metadata(thread_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(metadata
+        .as_ref()
+        .and_then(ThreadConfig::from_metadata)
+        .unwrap_or_default())
+}
+
+pub(crate) async fn chat_thread_config_handler(
+    State(state): State<Arc<GatewayState>>,
+    Path(thread_id): Path<String>,
+) -> Result<Json<ThreadConfigResponse>, (StatusCode, String)> {
+    let thread_id = crate::channels::web::server::parse_uuid(&thread_id, "thread_id")?;
+    let config = load_owned_thread_config(state.as_ref(), thread_id).await?;
+    Ok(Json(ThreadConfigResponse { thread_id, config }))
+}
+
+/// Update a thread's system prompt additions, tone, and enabled toolset.
+/// Changes apply from the thread's next turn.
+pub(crate) async fn chat_thread_config_patch_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(thread_id): Path<String>,
+    Json(req): Json<UpdateThreadConfigRequest>,
+) -> Result<Json<ThreadConfigResponse>, (StatusCode, String)> {
+    let thread_id = crate::channels::web::server::parse_uuid(&thread_id, "thread_id")?;
+    let mut config = load_owned_thread_config(state.as_ref(), thread_id).await?;
+
+    if let Some(additions) =
+        crate::channels::web::server::parse_optional_matter_field_patch(req.system_prompt_additions)
+    {
+        if additions
+            .as_ref()
+            .is_some_and(|text| text.chars().count() > MAX_SYSTEM_PROMPT_ADDITIONS_CHARS)
+        {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "'system_prompt_additions' must be at most {MAX_SYSTEM_PROMPT_ADDITIONS_CHARS} characters"
+                ),
+            ));
+        }
+        config.system_prompt_additions = additions;
+    }
+    if let Some(tone) = crate::channels::web::server::parse_optional_matter_field_patch(req.tone) {
+        config.tone = tone
+            .map(|raw| {
+                ThreadTone::parse(&raw).ok_or_else(|| {
+                    let valid: Vec<&str> = ThreadTone::ALL.iter().map(|t| t.as_str()).collect();
+                    (
+                        StatusCode::BAD_REQUEST,
+                        format!("'tone' must be one of: {}", valid.join(", ")),
+                    )
+                })
+            })
+            .transpose()?;
+    }
+    if let Some(tools) = req.enabled_tools {
+        let mut names: Vec<String> = tools
+            .unwrap_or_default()
+            .into_iter()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect();
+        names.sort();
+        names.dedup();
+        if names.len() > MAX_ENABLED_TOOLS {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("'enabled_tools' may name at most {MAX_ENABLED_TOOLS} tools"),
+            ));
+        }
+        if let Some(registry) = state.tool_registry.as_ref() {
+            for name in &names {
+                if !registry.has(name).await {
+                    return Err((
+                        StatusCode::BAD_REQUEST,
+                        format!("Unknown tool '{name}' in 'enabled_tools'"),
+                    ));
+                }
+            }
+        }
+        config.enabled_tools = (!names.is_empty()).then_some(names);
+    }
+
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let value = if config.is_empty() {
+        serde_json::Value::Null
+    } else {
+        serde_json::to_value(&config)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    };
+    store
+        .update_conversation_metadata_field(thread_id, THREAD_CONFIG_METADATA_KEY, &value)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "chat_thread_config_updated",
+        &principal.user_id,
+        None,
+        AuditSeverity::Info,
+        serde_json::json!({
+            "thread_id": thread_id.to_string(),
+            "tone": config.tone.map(ThreadTone::as_str),
+            "system_prompt_additions_chars": config
+                .system_prompt_additions
+                .as_ref()
+                .map(|text| text.chars().count()),
+            "enabled_tools": config.enabled_tools,
+        }),
+    )
+    .await;
+    Ok(Json(ThreadConfigResponse { thread_id, config }))
+}
@@ -0,0 +1,145 @@
+//! Firm-wide dashboard handler.
+
+use std::sync::Arc;
+
+use axum::{Json, Router, extract::State, http::StatusCode, routing::get};
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+
+use crate::agent::BROKEN_TOOL_FAILURE_THRESHOLD;
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{MatterStatus, MatterTaskStatus};
+
+/// Days ahead that count as upcoming deadlines.
+const DASHBOARD_DEADLINE_DAYS: i64 = 14;
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new().route("/api/dashboard", get(dashboard_handler))
+}
+
+/// `GET /api/dashboard` — practice-wide overview across every open matter:
+/// deadlines in the next 14 days, overdue tasks, unbilled hours, active jobs,
+/// and broken tools. Closed and archived matters are left out.
+pub(crate) async fn dashboard_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+) -> Result<Json<FirmDashboardResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    // Same rule as the matter list: only the owner sees every matter.
+    if principal.user_id != state.user_id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Insufficient permissions".to_string(),
+        ));
+    }
+    let db_err =
+        |e: crate::error::DatabaseError| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+
+    let now = Utc::now();
+    let today = now.date_naive();
+    let horizon = today + Duration::days(DASHBOARD_DEADLINE_DAYS);
+    let matters = store
+        .list_matters_db(&state.user_id)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .filter(|m| !matches!(m.status, MatterStatus::Closed | MatterStatus::Archived))
+        .collect::<Vec<_>>();
+
+    let mut upcoming_deadlines = Vec::new();
+    let mut overdue_tasks = Vec::new();
+    let mut unbilled_hours = Decimal::ZERO;
+    let mut unbilled_by_matter = Vec::new();
+    for matter in &matters {
+        let matter_id = matter.matter_id.as_str();
+        for deadline in store
+            .list_matter_deadlines(&state.user_id, matter_id)
+            .await
+            .map_err(db_err)?
+        {
+            let due = deadline.due_at.date_naive();
+            if deadline.completed_at.is_none() && due >= today && due <= horizon {
+                upcoming_deadlines.push((
+                    deadline.due_at,
+                    DashboardDeadlineInfo {
+                        matter_id: matter_id.to_string(),
+                        deadline: crate::channels::web::server::deadline_record_to_info(deadline),
+                    },
+                ));
+            }
+        }
+        for task in store
+            .list_matter_tasks(&state.user_id, matter_id)
+            .await
+            .map_err(db_err)?
+        {
+            let open = matches!(
+                task.status,
+                MatterTaskStatus::Todo | MatterTaskStatus::InProgress | MatterTaskStatus::Blocked
+            );
+            if let Some(due_at) = task.due_at.filter(|due| open && *due < now) {
+                overdue_tasks.push((
+                    due_at,
+                    DashboardTaskInfo {
+                        matter_id: matter_id.to_string(),
+                        days_overdue: today.signed_duration_since(due_at.date_naive()).num_days(),
+                        task: crate::channels::web::server::matter_task_record_to_info(task),
+                    },
+                ));
+            }
+        }
+        let summary = store
+            .matter_time_summary(&state.user_id, matter_id)
+            .await
+            .map_err(db_err)?;
+        if summary.unbilled_hours > Decimal::ZERO {
+            unbilled_hours += summary.unbilled_hours;
+            unbilled_by_matter.push(DashboardUnbilledInfo {
+                matter_id: matter_id.to_string(),
+                unbilled_hours: summary.unbilled_hours.normalize().to_string(),
+            });
+        }
+    }
+    upcoming_deadlines.sort_by_key(|(due, _)| *due);
+    overdue_tasks.sort_by_key(|(due, _)| *due);
+
+    let mut active_jobs = store
+        .list_sandbox_jobs_for_user(&state.user_id)
+        .await
+        .map_err(db_err)?
+        .iter()
+        .filter(|job| matches!(job.status.as_str(), "creating" | "running"))
+        .map(crate::channels::web::server::sandbox_job_to_info)
+        .collect::<Vec<_>>();
+    active_jobs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    let broken_tools = store
+        .get_broken_tools(BROKEN_TOOL_FAILURE_THRESHOLD)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|tool| BrokenToolInfo {
+            name: tool.name,
+            failure_count: tool.failure_count,
+            last_error: tool.last_error,
+            last_failure: tool.last_failure.to_rfc3339(),
+            repair_attempts: tool.repair_attempts,
+        })
+        .collect();
+
+    Ok(Json(FirmDashboardResponse {
+        generated_at: now.to_rfc3339(),
+        matter_count: matters.len(),
+        upcoming_deadlines: upcoming_deadlines.into_iter().map(|(_, d)| d).collect(),
+        overdue_tasks: overdue_tasks.into_iter().map(|(_, t)| t).collect(),
+        unbilled_hours: unbilled_hours.normalize().to_string(),
+        unbilled_by_matter,
+        active_jobs,
+        broken_tools,
+    }))
+}
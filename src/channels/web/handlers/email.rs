@@ -0,0 +1,306 @@
+//! Inbound email filing.
+//!
+//! The firm's mail provider forwards each parsed inbound message to
+//! `POST /api/email/inbound/{token}`, where `token` is the secret from the
+//! `email_intake` setting. The message is filed into the matter named by a
+//! plus-addressed or `matter-` recipient, or by a subject tag (see
+//! [`crate::legal::email_intake`]): the email lands in the matter's
+//! `communications/inbox/`, plain-text attachments are classified and filed
+//! like uploads, and the contact log gets a row. Mail that names no existing
+//! matter goes to `email/unfiled/` for manual triage.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{DefaultBodyLimit, Path, State},
+    http::StatusCode,
+    routing::post,
+};
+use base64::Engine;
+use chrono::Utc;
+
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{AuditSeverity, MatterDocumentCategory};
+use crate::legal::correspondence::{
+    CONTACT_LOG_TEMPLATE, InboundEmail, contact_log_path, inbound_email_row, inbox_path,
+    render_inbound_email, unfiled_email_path,
+};
+
+/// Room for a few attachments at the upload size limit after base64.
+const MAX_INBOUND_EMAIL_BYTES: usize = 4 * crate::channels::web::server::UPLOAD_FILE_SIZE_LIMIT;
+
+pub fn public_routes() -> Router<Arc<GatewayState>> {
+    Router::new().route(
+        "/api/email/inbound/{token}",
+        post(email_inbound_handler).layer(DefaultBodyLimit::max(MAX_INBOUND_EMAIL_BYTES)),
+    )
+}
+
+/// First candidate that names a matter in the database or the workspace.
+async fn resolve_matter(state: &GatewayState, candidates: &[String]) -> Option<String> {
+    let matter_root = crate::channels::web::server::matter_root_for_gateway(state);
+    for candidate in candidates {
+        let in_db = match state.store.as_ref() {
+            Some(store) => matches!(
+                store.get_matter_db(&state.user_id, candidate).await,
+                Ok(Some(_))
+            ),
+            None => false,
+        };
+        if in_db
+            || crate::channels::web::server::read_workspace_matter_metadata_optional(
+                state.workspace.as_ref(),
+                &matter_root,
+                candidate,
+            )
+            .await
+            .is_some()
+        {
+            return Some(candidate.clone());
+        }
+    }
+    None
+}
+
+fn safe_attachment_name(raw: &str) -> String {
+    let name: String = raw
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, '.' | '-' | '_' | ' '))
+        .collect();
+    let name = name.trim().trim_start_matches('.');
+    if name.is_empty() {
+        "attachment.txt".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Decode a plain-text attachment, or say why it cannot be filed.
+fn attachment_text(attachment: &InboundEmailAttachment) -> Result<String, String> {
+    let encoded: String = attachment
+        .content
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect();
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|_| "content is not valid base64".to_string())?;
+    if bytes.len() > crate::channels::web::server::UPLOAD_FILE_SIZE_LIMIT {
+        return Err(format!(
+            "{} bytes exceeds the 10 MiB upload limit",
+            bytes.len()
+        ));
+    }
+    String::from_utf8(bytes).map_err(|_| {
+        format!(
+            "not plain text ({})",
+            attachment
+                .content_type
+                .as_deref()
+                .unwrap_or("application/octet-stream")
+        )
+    })
+}
+
+pub(crate) async fn email_inbound_handler(
+    State(state): State<Arc<GatewayState>>,
+    Path(token): Path<String>,
+    Json(req): Json<InboundEmailRequest>,
+) -> Result<Json<InboundEmailResponse>, (StatusCode, String)> {
+    let not_found = || (StatusCode::NOT_FOUND, "Not found".to_string());
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let settings = store
+        .get_setting(
+            &state.user_id,
+            crate::legal::email_intake::EMAIL_INTAKE_SETTING_KEY,
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .and_then(|value| crate::legal::email_intake::parse_setting_value(&value).ok())
+        .filter(|settings| settings.accepts_token(&token))
+        .ok_or_else(not_found)?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    if req.from.trim().is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "'from' is required".to_string()));
+    }
+
+    let recipients: Vec<String> = req
+        .to
+        .iter()
+        .chain(&req.cc)
+        .chain(&req.recipients)
+        .cloned()
+        .collect();
+    let candidates =
+        crate::legal::email_intake::matter_candidates(&recipients, &req.subject, &settings.domains);
+    let matter_id = resolve_matter(state.as_ref(), &candidates).await;
+    let email = InboundEmail {
+        from: req.from.trim().to_string(),
+        to: req.to.clone(),
+        cc: req.cc.clone(),
+        subject: if req.subject.trim().is_empty() {
+            "(no subject)".to_string()
+        } else {
+            req.subject.trim().to_string()
+        },
+        body: req.text.clone(),
+        received_at: Utc::now(),
+        message_id: req.message_id.clone(),
+    };
+
+    let mut filed_attachments = Vec::new();
+    let mut skipped_attachments = Vec::new();
+    let message_path = match matter_id.as_deref() {
+        Some(matter_id) => {
+            let matter_prefix =
+                crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), matter_id);
+            for attachment in &req.attachments {
+                let name = safe_attachment_name(&attachment.filename);
+                let content = match attachment_text(attachment) {
+                    Ok(content) => content,
+                    Err(reason) => {
+                        skipped_attachments.push(format!("{name} ({reason})"));
+                        continue;
+                    }
+                };
+                let classification = crate::legal::classify::classify_document(
+                    state.llm_provider.as_deref(),
+                    &name,
+                    &content,
+                )
+                .await;
+                let mut path =
+                    format!("{matter_prefix}/{}/{name}", classification.suggested_folder);
+                if workspace.read(&path).await.is_ok() {
+                    path = format!(
+                        "{matter_prefix}/{}/{}-{name}",
+                        classification.suggested_folder,
+                        email.received_at.format("%Y%m%d-%H%M%S")
+                    );
+                }
+                workspace
+                    .write(&path, &content)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+                crate::channels::web::server::register_ingested_matter_document(
+                    state.as_ref(),
+                    matter_id,
+                    &path,
+                    &name,
+                    classification.category,
+                )
+                .await?;
+                crate::channels::web::server::queue_ingested_party_candidates(
+                    state.as_ref(),
+                    matter_id,
+                    &path,
+                    &content,
+                )
+                .await;
+                filed_attachments.push(path);
+            }
+
+            let matter_root = crate::channels::web::server::matter_root_for_gateway(state.as_ref());
+            let path = inbox_path(&matter_root, matter_id, email.received_at, &email.subject);
+            let document = render_inbound_email(
+                &email,
+                Some(matter_id),
+                &filed_attachments,
+                &skipped_attachments,
+            );
+            workspace
+                .write(&path, &document)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            crate::channels::web::server::register_ingested_matter_document(
+                state.as_ref(),
+                matter_id,
+                &path,
+                &email.subject,
+                MatterDocumentCategory::Correspondence,
+            )
+            .await?;
+            crate::channels::web::server::queue_ingested_party_candidates(
+                state.as_ref(),
+                matter_id,
+                &path,
+                &email.body,
+            )
+            .await;
+
+            let log_path = contact_log_path(&matter_root, matter_id);
+            let existing = match workspace.read(&log_path).await {
+                Ok(doc) if !doc.content.trim().is_empty() => doc.content,
+                _ => CONTACT_LOG_TEMPLATE.to_string(),
+            };
+            let updated = format!(
+                "{}\n{}",
+                existing.trim_end(),
+                inbound_email_row(&email, &path)
+            );
+            if let Err(err) = workspace.write(&log_path, &updated).await {
+                tracing::warn!(matter_id = %matter_id, "failed to log inbound email: {err}");
+            }
+            path
+        }
+        None => {
+            skipped_attachments.extend(req.attachments.iter().map(|attachment| {
+                format!(
+                    "{} (message not filed to a matter)",
+                    safe_attachment_name(&attachment.filename)
+                )
+            }));
+            let path = unfiled_email_path(email.received_at, &email.subject);
+            let document = render_inbound_email(&email, None, &[], &skipped_attachments);
+            workspace
+                .write(&path, &document)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            path
+        }
+    };
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        if matter_id.is_some() {
+            "email_filed"
+        } else {
+            "email_unfiled"
+        },
+        &state.user_id,
+        matter_id.as_deref(),
+        if matter_id.is_some() {
+            AuditSeverity::Info
+        } else {
+            AuditSeverity::Warn
+        },
+        serde_json::json!({
+            "from": email.from.clone(),
+            "subject": email.subject.clone(),
+            "candidates": candidates,
+            "path": message_path.clone(),
+            "attachments": filed_attachments.clone(),
+            "skipped_attachments": skipped_attachments.len(),
+        }),
+    )
+    .await;
+
+    Ok(Json(InboundEmailResponse {
+        filed: matter_id.is_some(),
+        matter_id,
+        message_path,
+        attachments: filed_attachments,
+        skipped_attachments,
+    }))
+}
@@ -2,7 +2,13 @@
 
 use std::sync::Arc;
 
-use axum::{Json, Router, extract::State, routing::get};
+use axum::{
+    Json, Router,
+    extract::State,
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
 
 use crate::channels::web::state::GatewayState;
 use crate::channels::web::types::HealthResponse;
@@ -12,7 +18,9 @@ pub fn public_routes() -> Router<Arc<GatewayState>> {
 }
 
 pub fn routes() -> Router<Arc<GatewayState>> {
-    Router::new().route("/api/gateway/status", get(gateway_status_handler))
+    Router::new()
+        .route("/api/gateway/status", get(gateway_status_handler))
+        .route("/metrics", get(metrics_handler))
 }
 
 async fn health_handler() -> Json<HealthResponse> {
@@ -54,6 +62,8 @@ async fn gateway_status_handler(
         (None, None, None)
     };
 
+    let intake = state.intake.stats(state.msg_tx.read().await.as_ref());
+
     Json(GatewayStatusResponse {
         sse_connections,
         ws_connections,
@@ -62,9 +72,36 @@ async fn gateway_status_handler(
         daily_cost,
         actions_this_hour,
         model_usage,
+        intake,
+        rate_limits: state.rate_limiter.stats(),
     })
 }
 
+/// Prometheus scrape endpoint. Gauges mirroring live state are refreshed
+/// before rendering.
+async fn metrics_handler(State(state): State<Arc<GatewayState>>) -> Response {
+    use crate::observability::metrics;
+
+    let depth = state.intake.stats(state.msg_tx.read().await.as_ref()).depth;
+    metrics::set_intake_queue_depth("gateway", depth);
+    if let Some(ref jm) = state.job_manager {
+        let queue = jm.queue_snapshot().await;
+        metrics::set_sandbox_jobs(queue.active.len(), queue.queued.len());
+    }
+    if let Some(stats) = state.store.as_ref().and_then(|s| s.pool_stats()) {
+        metrics::set_db_pool(&stats);
+    }
+
+    match metrics::render() {
+        Some(body) => ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            "Metrics are disabled (METRICS_ENABLED=false)",
+        )
+            .into_response(),
+    }
+}
+
 #[derive(serde::Serialize)]
 struct ModelUsageEntry {
     model: String,
@@ -85,4 +122,8 @@ struct GatewayStatusResponse {
     actions_this_hour: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     model_usage: Option<Vec<ModelUsageEntry>>,
+    /// Agent-loop intake queue depth and rejection counters.
+    intake: crate::channels::IntakeStats,
+    /// Per-route-group rate limits and allowed/limited counters.
+    rate_limits: crate::channels::web::rate_limit::RateLimitStats,
 }
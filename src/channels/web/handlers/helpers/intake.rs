@@ -0,0 +1,26 @@
+//! Intake backpressure response helpers for web handlers.
+
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+
+use crate::channels::IntakeError;
+
+/// Map an intake rejection to a response. Backpressure rejections are 429s
+/// carrying a `Retry-After` header.
+pub(crate) fn intake_error_response(err: IntakeError) -> Response {
+    let status = match err {
+        IntakeError::QueueFull { .. } | IntakeError::UserLimit { .. } => {
+            StatusCode::TOO_MANY_REQUESTS
+        }
+        IntakeError::NotStarted => StatusCode::SERVICE_UNAVAILABLE,
+        IntakeError::Closed => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    let retry_after = err.retry_after();
+    let mut response = (status, err.to_string()).into_response();
+    if let Some(retry_after) = retry_after
+        && let Ok(value) = HeaderValue::from_str(&retry_after.as_secs().max(1).to_string())
+    {
+        response.headers_mut().insert(header::RETRY_AFTER, value);
+    }
+    response
+}
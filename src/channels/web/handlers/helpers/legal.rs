@@ -10,7 +10,7 @@ use crate::channels::web::state::GatewayState;
 use crate::db::AuditSeverity;
 use crate::workspace::paths;
 
-pub(crate) const MATTER_ROOT: &str = "matters";
+pub(crate) use crate::legal::policy::DEFAULT_MATTER_ROOT as MATTER_ROOT;
 /// Settings key used to persist the active matter ID.
 pub(crate) const MATTER_ACTIVE_SETTING: &str = "legal.active_matter";
 /// Maximum number of party names accepted by intake conflict endpoints.
@@ -17,6 +17,22 @@ pub(crate) fn client_record_to_info(client: crate::db::ClientRecord) -> ClientIn
     }
 }
 
+pub(crate) fn sandbox_job_to_info(job: &crate::history::SandboxJobRecord) -> JobInfo {
+    let ui_state = match job.status.as_str() {
+        "creating" => "pending",
+        "running" => "in_progress",
+        s => s,
+    };
+    JobInfo {
+        id: job.id,
+        title: job.task.clone(),
+        state: ui_state.to_string(),
+        user_id: job.user_id.clone(),
+        created_at: job.created_at.to_rfc3339(),
+        started_at: job.started_at.map(|dt| dt.to_rfc3339()),
+    }
+}
+
 pub(crate) fn matter_task_record_to_info(task: crate::db::MatterTaskRecord) -> MatterTaskInfo {
     MatterTaskInfo {
         id: task.id.to_string(),
@@ -30,6 +46,9 @@ pub(crate) fn matter_task_record_to_info(task: crate::db::MatterTaskRecord) -> M
             .into_iter()
             .map(|id| id.to_string())
             .collect(),
+        position: task.position,
+        parent_task_id: task.parent_task_id.map(|id| id.to_string()),
+        checklist: task.checklist,
         created_at: task.created_at.to_rfc3339(),
         updated_at: task.updated_at.to_rfc3339(),
     }
@@ -41,6 +60,7 @@ pub(crate) fn matter_note_record_to_info(note: crate::db::MatterNoteRecord) -> M
         author: note.author,
         body: note.body,
         pinned: note.pinned,
+        parent_id: note.parent_id.map(|id| id.to_string()),
         created_at: note.created_at.to_rfc3339(),
         updated_at: note.updated_at.to_rfc3339(),
     }
@@ -300,6 +320,52 @@ pub(crate) fn billing_rate_schedule_record_to_info(
     }
 }
 
+pub(crate) fn matter_fact_record_to_info(record: crate::db::MatterFactRecord) -> MatterFactInfo {
+    MatterFactInfo {
+        id: record.id.to_string(),
+        date: record.fact_date.format("%Y-%m-%d").to_string(),
+        description: record.description,
+        source_path: record.source_path,
+        source_locator: record.source_locator,
+        source_excerpt: record.source_excerpt,
+        confidence: record.confidence,
+        extractor: record.extractor,
+        created_by: record.created_by,
+        created_at: record.created_at.to_rfc3339(),
+    }
+}
+
+/// Merge document entity rows (ordered by kind and normalized value) into
+/// one entry per entity with its document mentions.
+pub(crate) fn document_entity_records_to_info(
+    records: Vec<crate::db::DocumentEntityRecord>,
+) -> Vec<MatterEntityInfo> {
+    let mut out: Vec<MatterEntityInfo> = Vec::new();
+    for record in records {
+        let mention = MatterEntityMention {
+            source_path: record.source_path,
+            snippet: record.snippet,
+        };
+        match out.last_mut() {
+            Some(last)
+                if last.kind == record.kind.as_str()
+                    && last.normalized_value == record.normalized_value =>
+            {
+                last.confidence = last.confidence.max(record.confidence);
+                last.mentions.push(mention);
+            }
+            _ => out.push(MatterEntityInfo {
+                kind: record.kind.as_str().to_string(),
+                value: record.value,
+                normalized_value: record.normalized_value,
+                confidence: record.confidence,
+                mentions: vec![mention],
+            }),
+        }
+    }
+    out
+}
+
 pub(crate) fn matter_party_record_to_info(record: crate::db::MatterPartyRecord) -> MatterPartyInfo {
     MatterPartyInfo {
         id: record.id.to_string(),
@@ -316,6 +382,28 @@ pub(crate) fn matter_party_record_to_info(record: crate::db::MatterPartyRecord)
     }
 }
 
+pub(crate) fn party_candidate_record_to_info(
+    record: crate::db::PartyCandidateRecord,
+    conflict_hits: Vec<crate::db::ConflictHit>,
+) -> PartyCandidateInfo {
+    PartyCandidateInfo {
+        id: record.id.to_string(),
+        matter_id: record.matter_id,
+        name: record.name,
+        entity_type: record.entity_type.as_str().to_string(),
+        source_path: record.source_path,
+        snippet: record.snippet,
+        confidence: record.confidence,
+        extractor: record.extractor,
+        status: record.status.as_str().to_string(),
+        party_id: record.party_id.map(|value| value.to_string()),
+        reviewed_by: record.reviewed_by,
+        reviewed_at: record.reviewed_at.map(|value| value.to_rfc3339()),
+        created_at: record.created_at.to_rfc3339(),
+        conflict_hits,
+    }
+}
+
 pub(crate) fn party_relationship_record_to_info(
     record: crate::db::PartyRelationshipRecord,
 ) -> PartyRelationshipInfo {
@@ -348,6 +436,155 @@ pub(crate) fn conflict_clearance_info_to_response(
     }
 }
 
+pub(crate) fn document_translation_record_to_info(
+    record: crate::db::DocumentTranslationRecord,
+) -> DocumentTranslationInfo {
+    DocumentTranslationInfo {
+        id: record.id.to_string(),
+        matter_id: record.matter_id,
+        source_path: record.source_path,
+        source_language: record.source_language,
+        target_language: record.target_language,
+        translation_path: record.translation_path,
+        provider: record.provider,
+        status: record.status.as_str().to_string(),
+        translator: record.translator,
+        certified_path: record.certified_path,
+        certified_at: record.certified_at.map(|ts| ts.to_rfc3339()),
+        created_by: record.created_by,
+        created_at: record.created_at.to_rfc3339(),
+        updated_at: record.updated_at.to_rfc3339(),
+    }
+}
+
+pub(crate) fn matter_exhibit_record_to_info(
+    record: crate::db::MatterExhibitRecord,
+    matter_prefix: &str,
+) -> MatterExhibitInfo {
+    MatterExhibitInfo {
+        id: record.id.to_string(),
+        cover_sheet_path: crate::legal::exhibits::cover_sheet_path(matter_prefix, &record.label),
+        matter_id: record.matter_id,
+        label: record.label,
+        party_prefix: record.party_prefix,
+        sequence: record.sequence,
+        path: record.path,
+        matter_document_id: record.matter_document_id.map(|id| id.to_string()),
+        description: record.description,
+        status: record.status.as_str().to_string(),
+        created_by: record.created_by,
+        created_at: record.created_at.to_rfc3339(),
+        withdrawn_at: record.withdrawn_at.map(|ts| ts.to_rfc3339()),
+    }
+}
+
+pub(crate) fn settlement_proposal_record_to_info(
+    record: crate::db::SettlementProposalRecord,
+) -> SettlementProposalInfo {
+    SettlementProposalInfo {
+        id: record.id.to_string(),
+        matter_id: record.matter_id,
+        kind: record.kind.as_str().to_string(),
+        side: record.side.as_str().to_string(),
+        from_party: record.from_party,
+        to_party: record.to_party,
+        amount: record.amount.map(|amount| amount.to_string()),
+        terms: record.terms,
+        proposed_on: record.proposed_on.to_string(),
+        expires_on: record.expires_on.map(|date| date.to_string()),
+        status: record.status.as_str().to_string(),
+        response_notes: record.response_notes,
+        created_by: record.created_by,
+        created_at: record.created_at.to_rfc3339(),
+        updated_at: record.updated_at.to_rfc3339(),
+    }
+}
+
+pub(crate) fn settlement_authority_record_to_info(
+    record: crate::db::SettlementAuthorityRecord,
+) -> SettlementAuthorityInfo {
+    SettlementAuthorityInfo {
+        id: record.id.to_string(),
+        matter_id: record.matter_id,
+        kind: record.kind.as_str().to_string(),
+        amount: record.amount.to_string(),
+        granted_on: record.granted_on.to_string(),
+        granted_by: record.granted_by,
+        notes: record.notes,
+        created_by: record.created_by,
+        created_at: record.created_at.to_rfc3339(),
+    }
+}
+
+pub(crate) fn precedent_record_to_info(record: crate::db::PrecedentRecord) -> PrecedentInfo {
+    PrecedentInfo {
+        id: record.id.to_string(),
+        title: record.title,
+        path: record.path,
+        source_matter_id: record.source_matter_id,
+        source_path: record.source_path,
+        document_type: record.document_type,
+        practice_area: record.practice_area,
+        jurisdiction: record.jurisdiction,
+        promoted_by: record.promoted_by,
+        created_at: record.created_at.to_rfc3339(),
+    }
+}
+
+pub(crate) fn client_communication_preferences_record_to_info(
+    record: crate::db::ClientCommunicationPreferencesRecord,
+) -> ClientCommunicationPreferencesInfo {
+    ClientCommunicationPreferencesInfo {
+        allowed_channels: record
+            .allowed_channels
+            .iter()
+            .map(|channel| channel.as_str().to_string())
+            .collect(),
+        quiet_hours: record.quiet_hours,
+        timezone: record.timezone,
+        language: record.language,
+        disclaimers: record.disclaimers,
+        updated_by: record.updated_by,
+        created_at: record.created_at.to_rfc3339(),
+        updated_at: record.updated_at.to_rfc3339(),
+    }
+}
+
+pub(crate) fn client_communication_consent_record_to_info(
+    record: crate::db::ClientCommunicationConsentRecord,
+) -> ClientCommunicationConsentInfo {
+    ClientCommunicationConsentInfo {
+        id: record.id.to_string(),
+        channel: record.channel.as_str().to_string(),
+        status: record.status.as_str().to_string(),
+        source: record.source,
+        consented_at: record.consented_at.to_rfc3339(),
+        recorded_by: record.recorded_by,
+        created_at: record.created_at.to_rfc3339(),
+    }
+}
+
+pub(crate) fn client_status_report_record_to_info(
+    record: crate::db::ClientStatusReportRecord,
+) -> ClientStatusReportInfo {
+    ClientStatusReportInfo {
+        id: record.id.to_string(),
+        matter_id: record.matter_id,
+        report_path: record.report_path,
+        subject: record.subject,
+        recipient: record.recipient,
+        status: record.status.as_str().to_string(),
+        reviewed_by: record.reviewed_by,
+        review_note: record.review_note,
+        reviewed_at: record.reviewed_at.map(|ts| ts.to_rfc3339()),
+        sent_at: record.sent_at.map(|ts| ts.to_rfc3339()),
+        delivery_error: record.delivery_error,
+        created_by: record.created_by,
+        created_at: record.created_at.to_rfc3339(),
+        updated_at: record.updated_at.to_rfc3339(),
+    }
+}
+
 pub(crate) fn citation_verification_run_record_to_info(
     record: crate::db::CitationVerificationRunRecord,
 ) -> CitationVerificationRunInfo {
@@ -394,3 +631,37 @@ pub(crate) fn audit_event_record_to_info(
         details: event.details,
     }
 }
+
+pub(crate) fn document_tag_record_to_info(tag: crate::db::DocumentTagRecord) -> DocumentTagInfo {
+    DocumentTagInfo {
+        key: tag.key,
+        value: tag.value,
+        created_by: tag.created_by,
+        updated_at: tag.updated_at.to_rfc3339(),
+    }
+}
+
+pub(crate) fn trashed_document_to_info(doc: crate::workspace::TrashedDocument) -> TrashEntryInfo {
+    TrashEntryInfo {
+        id: doc.id.to_string(),
+        size_bytes: doc.content.len(),
+        path: doc.path,
+        deleted_by: doc.deleted_by,
+        deleted_at: doc.deleted_at.to_rfc3339(),
+        document_updated_at: doc.document_updated_at.to_rfc3339(),
+    }
+}
+
+pub(crate) fn document_revision_to_info(
+    revision: crate::workspace::DocumentRevision,
+    include_content: bool,
+) -> DocumentRevisionInfo {
+    DocumentRevisionInfo {
+        revision: revision.revision,
+        content_hash: revision.content_hash,
+        size_bytes: revision.content.len(),
+        created_at: revision.created_at.to_rfc3339(),
+        diff: revision.diff,
+        content: include_content.then_some(revision.content),
+    }
+}
@@ -11,16 +11,23 @@ use uuid::Uuid;
 use crate::channels::web::state::GatewayState;
 use crate::channels::web::types::*;
 use crate::db::{
-    ClientType, CreateClientParams, CreateDocumentVersionParams, CreateMatterDeadlineParams,
+    AuditSeverity, ClientType, CommunicationChannel, CreateClientParams,
+    CreateDocumentVersionParams, CreateMatterDeadlineParams, MatterDocumentCategory,
     MatterMemberRole, MatterStatus, UpsertDocumentTemplateParams, UpsertMatterDocumentParams,
     UpsertMatterParams,
 };
+use crate::events::DomainEvent;
+use crate::legal::client_comms::ClientCommsPolicy;
 use crate::workspace::Workspace;
 
 use super::legal::{
     matter_metadata_path_for_gateway, matter_prefix_for_gateway, matter_root_for_gateway,
+    record_legal_audit_event,
 };
-use super::parsing::{infer_matter_document_category, parse_optional_datetime};
+use super::parsing::parse_optional_datetime;
+
+/// Conflict-graph hits checked for candidates queued from one upload.
+const MAX_INGESTED_CONFLICT_HITS: usize = 20;
 
 #[derive(Debug, Default, Deserialize)]
 pub(crate) struct MatterDocumentsQuery {
@@ -69,6 +76,61 @@ pub(crate) async fn ensure_existing_matter_for_route(
     }
 }
 
+/// Sanitize the route id, check the principal holds at least `role` on the
+/// matter, and make sure the matter exists in the workspace.
+pub(crate) async fn matter_for_route(
+    state: &GatewayState,
+    principal_user_id: &str,
+    raw_matter_id: &str,
+    role: MatterMemberRole,
+) -> Result<String, (StatusCode, String)> {
+    let matter_id_guard = sanitize_matter_id_for_route(raw_matter_id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id_guard,
+        principal_user_id,
+        role,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let matter_root = matter_root_for_gateway(state);
+    ensure_existing_matter_for_route(workspace.as_ref(), &matter_root, raw_matter_id).await
+}
+
+/// [`matter_for_route`] for handlers that write matter-scoped rows: also
+/// backfills the matter's database row from its workspace metadata.
+pub(crate) async fn db_matter_for_route(
+    state: &GatewayState,
+    principal_user_id: &str,
+    raw_matter_id: &str,
+    role: MatterMemberRole,
+) -> Result<String, (StatusCode, String)> {
+    let matter_id = matter_for_route(state, principal_user_id, raw_matter_id, role).await?;
+    ensure_matter_db_row_from_workspace(state, &matter_id).await?;
+    Ok(matter_id)
+}
+
+/// A request path that must name a document inside the matter folder.
+pub(crate) fn matter_document_path(
+    field: &str,
+    raw: &str,
+    matter_prefix: &str,
+) -> Result<String, (StatusCode, String)> {
+    let path = raw.trim().trim_start_matches('/');
+    if !path.starts_with(&format!("{matter_prefix}/")) || path.split('/').any(|p| p == "..") {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("'{field}' must be a document under '{matter_prefix}/'"),
+        ));
+    }
+    Ok(path.to_string())
+}
+
 pub(crate) fn parse_template_name(raw: &str) -> Result<String, (StatusCode, String)> {
     let trimmed = raw.trim();
     if trimmed.is_empty() {
@@ -143,33 +205,15 @@ pub(crate) async fn choose_generated_document_destination(
     template_name: &str,
     timestamp: &str,
 ) -> Result<String, (StatusCode, String)> {
-    let parsed = FsPath::new(template_name);
-    let stem = parsed
-        .file_stem()
-        .and_then(|value| value.to_str())
-        .filter(|value| !value.trim().is_empty())
-        .unwrap_or("generated-document");
-    let ext = parsed
-        .extension()
-        .and_then(|value| value.to_str())
-        .filter(|value| !value.trim().is_empty())
-        .unwrap_or("md");
-
-    for counter in 1usize..=999 {
-        let suffix = if counter == 1 {
-            String::new()
-        } else {
-            format!("-{}", counter)
-        };
-        let candidate = format!("{matter_prefix}/drafts/{stem}-{timestamp}{suffix}.{ext}");
-        match workspace.read(&candidate).await {
-            Ok(_) => continue,
-            Err(crate::error::WorkspaceError::DocumentNotFound { .. }) => return Ok(candidate),
-            Err(err) => return Err((StatusCode::INTERNAL_SERVER_ERROR, err.to_string())),
-        }
-    }
-
-    Err((
+    crate::legal::docgen::generated_document_path(
+        workspace,
+        matter_prefix,
+        template_name,
+        timestamp,
+    )
+    .await
+    .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+    .ok_or((
         StatusCode::INTERNAL_SERVER_ERROR,
         "Failed to pick a unique destination for generated document".to_string(),
     ))
@@ -392,7 +436,8 @@ fn parse_deadlines_from_calendar(markdown: &str, today: NaiveDate) -> Vec<Matter
     deadlines.into_iter().map(|(_, info)| info).collect()
 }
 
-async fn read_matter_deadlines(
+/// Dated rows of the matter's `deadlines/calendar.md` docket.
+pub(crate) async fn read_matter_deadlines(
     workspace: &Workspace,
     matter_prefix: &str,
     today: NaiveDate,
@@ -517,6 +562,14 @@ pub(crate) async fn backfill_matter_templates_from_workspace(
             .read(&template.path)
             .await
             .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+        // Keep any variable schema already set through the templates API.
+        let variables_json = store
+            .get_document_template_by_name(&state.user_id, Some(matter_id), &template.name)
+            .await
+            .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+            .filter(|existing| existing.matter_id.as_deref() == Some(matter_id))
+            .map(|existing| existing.variables_json)
+            .unwrap_or_else(|| serde_json::json!([]));
         store
             .upsert_document_template(
                 &state.user_id,
@@ -524,7 +577,7 @@ pub(crate) async fn backfill_matter_templates_from_workspace(
                     matter_id: Some(matter_id.to_string()),
                     name: template.name,
                     body: doc.content,
-                    variables_json: serde_json::json!([]),
+                    variables_json,
                 },
             )
             .await
@@ -559,7 +612,8 @@ pub(crate) async fn backfill_matter_documents_from_workspace(
                     memory_document_id: doc.id,
                     path: doc.path.clone(),
                     display_name: entry.name.clone(),
-                    category: infer_matter_document_category(&entry.path),
+                    category: crate::legal::classify::classify_heuristic(&entry.path, &doc.content)
+                        .category,
                     readiness_state: None,
                 },
             )
@@ -587,6 +641,155 @@ pub(crate) async fn backfill_matter_documents_from_workspace(
     Ok(())
 }
 
+/// Link a freshly ingested workspace file to the matter's document register
+/// with an initial version. No-op without a database.
+pub(crate) async fn register_ingested_matter_document(
+    state: &GatewayState,
+    matter_id: &str,
+    path: &str,
+    display_name: &str,
+    category: MatterDocumentCategory,
+) -> Result<(), (StatusCode, String)> {
+    let (Some(store), Some(workspace)) = (state.store.as_ref(), state.workspace.as_ref()) else {
+        return Ok(());
+    };
+    ensure_matter_db_row_from_workspace(state, matter_id).await?;
+    let doc = workspace
+        .read(path)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let linked = store
+        .upsert_matter_document(
+            &state.user_id,
+            matter_id,
+            &UpsertMatterDocumentParams {
+                memory_document_id: doc.id,
+                path: doc.path.clone(),
+                display_name: display_name.to_string(),
+                category,
+                readiness_state: None,
+            },
+        )
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let versions = store
+        .list_document_versions(&state.user_id, linked.id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    if versions.is_empty() {
+        store
+            .create_document_version(
+                &state.user_id,
+                &CreateDocumentVersionParams {
+                    matter_document_id: linked.id,
+                    label: "initial".to_string(),
+                    memory_document_id: doc.id,
+                },
+            )
+            .await
+            .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Extract entities from an ingested document: record its people,
+/// organizations, dates, and amounts in the document entity store, queue the
+/// names for conflict-graph review, check newly queued names against the
+/// conflict graph, and scan the text for watchlisted names. Extraction is best effort: failures are logged and never
+/// fail the upload. Returns the number of candidates queued.
+pub(crate) async fn queue_ingested_party_candidates(
+    state: &GatewayState,
+    matter_id: &str,
+    path: &str,
+    content: &str,
+) -> usize {
+    let Some(store) = state.store.as_ref() else {
+        return 0;
+    };
+    let entities =
+        crate::legal::entities::extract_entities(state.llm_provider.as_deref(), path, content)
+            .await;
+    let document_entities = crate::legal::entities::document_entities(content, &entities);
+    if let Err(err) = store
+        .replace_document_entities(&state.user_id, matter_id, path, &document_entities)
+        .await
+    {
+        tracing::warn!(matter_id = %matter_id, "failed to record document entities: {err}");
+    }
+    crate::channels::web::handlers::watchlist::scan_document_against_watchlist(
+        state, matter_id, path, content,
+    )
+    .await;
+    if entities.is_empty() {
+        return 0;
+    }
+    let queued = match crate::legal::entities::queue_party_candidates(
+        store.as_ref(),
+        matter_id,
+        path,
+        &entities,
+    )
+    .await
+    {
+        Ok(queued) => queued,
+        Err(err) => {
+            tracing::warn!(matter_id = %matter_id, "failed to queue party candidates: {err}");
+            return 0;
+        }
+    };
+    if queued.is_empty() {
+        return 0;
+    }
+    record_legal_audit_event(
+        state,
+        "party_candidates_extracted",
+        &state.user_id,
+        Some(matter_id),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "source_path": path,
+            "count": queued.len(),
+        }),
+    )
+    .await;
+
+    match crate::legal::entities::candidate_conflict_hits(
+        store.as_ref(),
+        matter_id,
+        &queued,
+        MAX_INGESTED_CONFLICT_HITS,
+    )
+    .await
+    {
+        Ok(hits) if !hits.is_empty() => {
+            record_legal_audit_event(
+                state,
+                "conflict_detected",
+                &state.user_id,
+                Some(matter_id),
+                AuditSeverity::Warn,
+                serde_json::json!({
+                    "source": "document_entity_extraction",
+                    "source_path": path,
+                    "hit_count": hits.len(),
+                    "top_conflict": hits.first().map(|hit| hit.party.clone()),
+                }),
+            )
+            .await;
+            state.publish_event(DomainEvent::ConflictHit {
+                matter_id: Some(matter_id.to_string()),
+                source: "document_entity_extraction".to_string(),
+                hit_count: hits.len(),
+            });
+        }
+        Ok(_) => {}
+        Err(err) => {
+            tracing::warn!(matter_id = %matter_id, "conflict check for party candidates failed: {err}");
+        }
+    }
+    queued.len()
+}
+
 pub(crate) async fn choose_filing_package_destination(
     workspace: &Workspace,
     matter_prefix: &str,
@@ -894,19 +1097,124 @@ pub(crate) async fn disable_deadline_reminder_routines(
     Ok(())
 }
 
+/// Opted-in SMS number of the matter's client, if any, with the client's
+/// communication preferences. Reminders are also texted to this number.
+async fn client_sms_reminder_target(
+    state: &GatewayState,
+    matter_id: &str,
+) -> Result<Option<(String, ClientCommsPolicy)>, (StatusCode, String)> {
+    let Some(store) = state.store.as_ref() else {
+        return Ok(None);
+    };
+    let Some(matter) = store
+        .get_matter_db(&state.user_id, matter_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+    else {
+        return Ok(None);
+    };
+    let consents = store
+        .list_sms_consents(&state.user_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let Some(phone) = consents
+        .into_iter()
+        .find(|consent| {
+            consent.client_id == Some(matter.client_id)
+                && consent.status == crate::db::SmsConsentStatus::OptedIn
+        })
+        .map(|consent| consent.phone_number)
+    else {
+        return Ok(None);
+    };
+    let policy =
+        crate::legal::client_comms::load_policy(store.as_ref(), &state.user_id, matter.client_id)
+            .await
+            .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    Ok(Some((phone, policy)))
+}
+
+/// Create the named one-shot reminder routine, or re-enable and update it.
+async fn upsert_reminder_routine(
+    state: &GatewayState,
+    name: String,
+    description: String,
+    schedule: String,
+    action: crate::agent::routine::RoutineAction,
+    notify: crate::agent::routine::NotifyConfig,
+    state_json: serde_json::Value,
+) -> Result<(), (StatusCode, String)> {
+    let Some(store) = state.store.as_ref() else {
+        return Ok(());
+    };
+    let next_fire = crate::agent::routine::next_cron_fire(&schedule)
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+
+    if let Some(mut existing) = store
+        .get_routine_by_name(&state.user_id, &name)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+    {
+        existing.enabled = true;
+        existing.trigger = crate::agent::routine::Trigger::Cron {
+            schedule,
+            timezone: None,
+            jitter_secs: 0,
+        };
+        existing.action = action;
+        existing.notify = notify;
+        existing.next_fire_at = next_fire;
+        existing.state = state_json;
+        return store
+            .update_routine(&existing)
+            .await
+            .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()));
+    }
+
+    let now = Utc::now();
+    let routine = crate::agent::routine::Routine {
+        id: Uuid::new_v4(),
+        name,
+        description,
+        user_id: state.user_id.clone(),
+        enabled: true,
+        trigger: crate::agent::routine::Trigger::Cron {
+            schedule,
+            timezone: None,
+            jitter_secs: 0,
+        },
+        action,
+        guardrails: crate::agent::routine::RoutineGuardrails::default(),
+        notify,
+        chain: crate::agent::routine::RoutineChain::default(),
+        last_run_at: None,
+        next_fire_at: next_fire,
+        run_count: 0,
+        consecutive_failures: 0,
+        state: state_json,
+        created_at: now,
+        updated_at: now,
+    };
+    store
+        .create_routine(&routine)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))
+}
+
 pub(crate) async fn sync_deadline_reminder_routines_for_record(
     state: &GatewayState,
     record: &crate::db::MatterDeadlineRecord,
 ) -> Result<(), (StatusCode, String)> {
     disable_deadline_reminder_routines(state, &record.matter_id, record.id).await?;
-    let Some(store) = state.store.as_ref() else {
+    if state.store.is_none() {
         return Ok(());
-    };
+    }
 
     if record.completed_at.is_some() || record.reminder_days.is_empty() {
         return Ok(());
     }
 
+    let sms_target = client_sms_reminder_target(state, &record.matter_id).await?;
     let now = Utc::now();
     for reminder_days in &record.reminder_days {
         let run_at = record.due_at - chrono::Duration::days(i64::from(*reminder_days));
@@ -916,8 +1224,6 @@ pub(crate) async fn sync_deadline_reminder_routines_for_record(
 
         let name = deadline_reminder_name(&record.matter_id, record.id, *reminder_days);
         let schedule = deadline_reminder_schedule(run_at);
-        let next_fire = crate::agent::routine::next_cron_fire(&schedule)
-            .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
         let prompt = format!(
             "Matter `{}` deadline reminder: \"{}\" is due on {} ({} days remaining). Provide a concise reminder and immediate next action.",
             record.matter_id,
@@ -932,56 +1238,77 @@ pub(crate) async fn sync_deadline_reminder_routines_for_record(
             "reminder_days": reminder_days,
         });
 
-        if let Some(mut existing) = store
-            .get_routine_by_name(&state.user_id, &name)
-            .await
-            .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
-        {
-            existing.enabled = true;
-            existing.trigger = crate::agent::routine::Trigger::Cron { schedule };
-            existing.action = crate::agent::routine::RoutineAction::Lightweight {
-                prompt: prompt.clone(),
+        upsert_reminder_routine(
+            state,
+            name.clone(),
+            format!(
+                "One-shot reminder {} day(s) before deadline '{}'",
+                reminder_days, record.title
+            ),
+            schedule,
+            crate::agent::routine::RoutineAction::Lightweight {
+                prompt,
                 context_paths: vec![matter_metadata_path_for_gateway(state, &record.matter_id)],
                 max_tokens: 300,
-            };
-            existing.next_fire_at = next_fire;
-            existing.state = state_json.clone();
-            store
-                .update_routine(&existing)
-                .await
-                .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+            },
+            crate::agent::routine::NotifyConfig::default(),
+            state_json.clone(),
+        )
+        .await?;
+
+        // The client-facing text shares the name prefix, so disabling the
+        // deadline's reminders disables it too.
+        let Some((ref phone, ref policy)) = sms_target else {
             continue;
+        };
+        // A text due in the client's quiet hours goes out when they end, as
+        // long as that is still before the deadline. The SMS channel clears
+        // it again when it is sent.
+        let sms_at = match policy.check(CommunicationChannel::Sms, run_at, false) {
+            Ok(_) => run_at,
+            Err(block) => match block.retry_at {
+                Some(retry_at) if retry_at < record.due_at => retry_at,
+                _ => continue,
+            },
+        };
+        let Ok(clearance) = policy.check(CommunicationChannel::Sms, sms_at, false) else {
+            continue;
+        };
+        let due_on = clearance
+            .locale()
+            .format_date(record.due_at.date_naive(), "long")
+            .unwrap_or_else(|_| record.due_at.date_naive().to_string());
+        let mut prompt = format!(
+            "Write a plain-text SMS reminder, under 300 characters, to the client of matter `{}`: \"{}\" is due on {}. Say what the client needs to do, if anything. Do not include legal analysis, privileged details, or internal notes. Reply with the message text only.",
+            record.matter_id, record.title, due_on,
+        );
+        if let Some(instruction) = clearance.drafting_instruction(sms_at.date_naive()) {
+            prompt.push(' ');
+            prompt.push_str(&instruction);
         }
-
-        let routine = crate::agent::routine::Routine {
-            id: Uuid::new_v4(),
-            name,
-            description: format!(
-                "One-shot reminder {} day(s) before deadline '{}'",
+        upsert_reminder_routine(
+            state,
+            format!("{name}-sms"),
+            format!(
+                "One-shot client SMS reminder {} day(s) before deadline '{}'",
                 reminder_days, record.title
             ),
-            user_id: state.user_id.clone(),
-            enabled: true,
-            trigger: crate::agent::routine::Trigger::Cron { schedule },
-            action: crate::agent::routine::RoutineAction::Lightweight {
+            deadline_reminder_schedule(sms_at),
+            crate::agent::routine::RoutineAction::Lightweight {
                 prompt,
                 context_paths: vec![matter_metadata_path_for_gateway(state, &record.matter_id)],
-                max_tokens: 300,
+                max_tokens: 200,
             },
-            guardrails: crate::agent::routine::RoutineGuardrails::default(),
-            notify: crate::agent::routine::NotifyConfig::default(),
-            last_run_at: None,
-            next_fire_at: next_fire,
-            run_count: 0,
-            consecutive_failures: 0,
-            state: state_json,
-            created_at: now,
-            updated_at: now,
-        };
-        store
-            .create_routine(&routine)
-            .await
-            .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+            crate::agent::routine::NotifyConfig {
+                channel: Some("sms".to_string()),
+                user: phone.clone(),
+                on_attention: true,
+                on_failure: false,
+                on_success: false,
+            },
+            state_json,
+        )
+        .await?;
     }
 
     Ok(())
@@ -998,15 +1325,6 @@ pub(crate) fn list_matters_root_entries(
 
 // ==================== RBAC Guard ====================
 
-/// Role hierarchy rank — higher value = more permissive.
-fn role_rank(role: &MatterMemberRole) -> u8 {
-    match role {
-        MatterMemberRole::Viewer => 1,
-        MatterMemberRole::Collaborator => 2,
-        MatterMemberRole::Owner => 3,
-    }
-}
-
 /// Verify that `requesting_user_id` holds at least `minimum_role` for the matter.
 ///
 /// When no store is configured (workspace-only mode), access is always granted
@@ -1033,7 +1351,7 @@ pub async fn require_matter_access(
             StatusCode::INTERNAL_SERVER_ERROR
         })?
         .ok_or(StatusCode::FORBIDDEN)?;
-    if role_rank(&role) < role_rank(&minimum_role) {
+    if role.rank() < minimum_role.rank() {
         return Err(StatusCode::FORBIDDEN);
     }
     Ok(role)
@@ -1,3 +1,4 @@
+pub(crate) mod intake;
 pub(crate) mod legal;
 pub(crate) mod mappers;
 pub(crate) mod matter;
@@ -243,21 +243,6 @@ pub(crate) fn parse_matter_document_category(
     }
 }
 
-pub(crate) fn infer_matter_document_category(path: &str) -> MatterDocumentCategory {
-    let lower = path.to_ascii_lowercase();
-    if lower.contains("/filing") || lower.contains("/pleading") {
-        MatterDocumentCategory::Filing
-    } else if lower.contains("/evidence") {
-        MatterDocumentCategory::Evidence
-    } else if lower.contains("/contract") || lower.contains("/agreement") {
-        MatterDocumentCategory::Contract
-    } else if lower.contains("/correspondence") || lower.contains("/communication") {
-        MatterDocumentCategory::Correspondence
-    } else {
-        MatterDocumentCategory::Internal
-    }
-}
-
 pub(crate) fn normalize_reminder_days(values: &[i32]) -> Result<Vec<i32>, (StatusCode, String)> {
     use std::collections::BTreeSet;
 
@@ -0,0 +1,255 @@
+//! Outbound webhook integration handlers.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, patch, post},
+};
+use uuid::Uuid;
+
+use crate::channels::web::auth::{AuthPrincipal, RequestPrincipal};
+use crate::channels::web::handlers::helpers::legal::record_legal_audit_event;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{AuditSeverity, UserRole};
+use crate::integrations::{
+    WebhookDispatcher, WebhookEndpoint, WebhookEndpointUpdate, WebhookError, WebhookEventKind,
+};
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new()
+        .route(
+            "/api/integrations/webhooks",
+            get(webhooks_list_handler).post(webhooks_create_handler),
+        )
+        .route(
+            "/api/integrations/webhooks/{id}",
+            patch(webhooks_update_handler).delete(webhooks_delete_handler),
+        )
+        .route(
+            "/api/integrations/webhooks/{id}/deliveries",
+            get(webhooks_deliveries_handler),
+        )
+        .route(
+            "/api/integrations/webhooks/{id}/test",
+            post(webhooks_test_handler),
+        )
+}
+
+fn endpoint_to_info(endpoint: WebhookEndpoint, include_secret: bool) -> WebhookEndpointInfo {
+    WebhookEndpointInfo {
+        id: endpoint.id.to_string(),
+        secret_hint: endpoint.secret_hint(),
+        secret: include_secret.then(|| endpoint.secret.clone()),
+        name: endpoint.name,
+        url: endpoint.url,
+        events: endpoint
+            .events
+            .iter()
+            .map(|kind| kind.as_str().to_string())
+            .collect(),
+        enabled: endpoint.enabled,
+        created_at: endpoint.created_at.to_rfc3339(),
+        updated_at: endpoint.updated_at.to_rfc3339(),
+    }
+}
+
+/// Webhooks send firm data off-box, so only administrators may configure them.
+fn require_admin(principal: &AuthPrincipal) -> Result<(), (StatusCode, String)> {
+    if principal.role != UserRole::Admin {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only administrators can manage webhook integrations".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn dispatcher(state: &GatewayState) -> Result<&Arc<WebhookDispatcher>, (StatusCode, String)> {
+    state.webhooks.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Webhook integrations not available".to_string(),
+    ))
+}
+
+fn parse_endpoint_id(raw: &str) -> Result<Uuid, (StatusCode, String)> {
+    Uuid::parse_str(raw).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            "Invalid webhook endpoint ID".to_string(),
+        )
+    })
+}
+
+fn parse_events(raw: &[String]) -> Result<Vec<WebhookEventKind>, (StatusCode, String)> {
+    raw.iter()
+        .map(|event| event.parse::<WebhookEventKind>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(webhook_error)
+}
+
+fn webhook_error(err: WebhookError) -> (StatusCode, String) {
+    let status = match err {
+        WebhookError::NotFound(_) => StatusCode::NOT_FOUND,
+        WebhookError::TooManyEndpoints => StatusCode::CONFLICT,
+        WebhookError::Storage(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        WebhookError::InvalidUrl(_)
+        | WebhookError::InvalidEvent(_)
+        | WebhookError::NoEvents
+        | WebhookError::InvalidName => StatusCode::BAD_REQUEST,
+    };
+    (status, err.to_string())
+}
+
+async fn audit_endpoint(
+    state: &GatewayState,
+    event_type: &str,
+    actor: &str,
+    endpoint: &WebhookEndpoint,
+    extra: serde_json::Value,
+) {
+    let mut details = serde_json::json!({
+        "endpoint_id": endpoint.id.to_string(),
+        "name": endpoint.name,
+        "url": endpoint.url,
+        "events": endpoint.events.iter().map(|k| k.as_str()).collect::<Vec<_>>(),
+        "enabled": endpoint.enabled,
+    });
+    if let (Some(details), serde_json::Value::Object(extra)) = (details.as_object_mut(), extra) {
+        details.extend(extra);
+    }
+    record_legal_audit_event(state, event_type, actor, None, AuditSeverity::Info, details).await;
+}
+
+pub(crate) async fn webhooks_list_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+) -> Result<Json<WebhookEndpointListResponse>, (StatusCode, String)> {
+    require_admin(&principal)?;
+    let endpoints = dispatcher(&state)?.list().await;
+    Ok(Json(WebhookEndpointListResponse {
+        endpoints: endpoints
+            .into_iter()
+            .map(|e| endpoint_to_info(e, false))
+            .collect(),
+        available_events: WebhookEventKind::SUBSCRIBABLE
+            .iter()
+            .map(|kind| kind.as_str())
+            .collect(),
+    }))
+}
+
+pub(crate) async fn webhooks_create_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Json(req): Json<CreateWebhookEndpointRequest>,
+) -> Result<(StatusCode, Json<WebhookEndpointInfo>), (StatusCode, String)> {
+    require_admin(&principal)?;
+    let events = parse_events(&req.events)?;
+    let endpoint = dispatcher(&state)?
+        .create(&req.name, &req.url, events)
+        .await
+        .map_err(webhook_error)?;
+    audit_endpoint(
+        &state,
+        "webhook_endpoint_created",
+        &principal.user_id,
+        &endpoint,
+        serde_json::json!({}),
+    )
+    .await;
+    Ok((StatusCode::CREATED, Json(endpoint_to_info(endpoint, true))))
+}
+
+pub(crate) async fn webhooks_update_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateWebhookEndpointRequest>,
+) -> Result<Json<WebhookEndpointInfo>, (StatusCode, String)> {
+    require_admin(&principal)?;
+    let id = parse_endpoint_id(&id)?;
+    let events = req.events.as_deref().map(parse_events).transpose()?;
+    let rotate_secret = req.rotate_secret;
+    let endpoint = dispatcher(&state)?
+        .update(
+            id,
+            WebhookEndpointUpdate {
+                name: req.name,
+                url: req.url,
+                events,
+                enabled: req.enabled,
+                rotate_secret,
+            },
+        )
+        .await
+        .map_err(webhook_error)?;
+    audit_endpoint(
+        &state,
+        "webhook_endpoint_updated",
+        &principal.user_id,
+        &endpoint,
+        serde_json::json!({ "secret_rotated": rotate_secret }),
+    )
+    .await;
+    Ok(Json(endpoint_to_info(endpoint, rotate_secret)))
+}
+
+pub(crate) async fn webhooks_delete_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    require_admin(&principal)?;
+    let endpoint = dispatcher(&state)?
+        .delete(parse_endpoint_id(&id)?)
+        .await
+        .map_err(webhook_error)?;
+    audit_endpoint(
+        &state,
+        "webhook_endpoint_deleted",
+        &principal.user_id,
+        &endpoint,
+        serde_json::json!({}),
+    )
+    .await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(crate) async fn webhooks_deliveries_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<WebhookDeliveryListResponse>, (StatusCode, String)> {
+    require_admin(&principal)?;
+    let id = parse_endpoint_id(&id)?;
+    let webhooks = dispatcher(&state)?;
+    if webhooks.get(id).await.is_none() {
+        return Err(webhook_error(WebhookError::NotFound(id)));
+    }
+    Ok(Json(WebhookDeliveryListResponse {
+        endpoint_id: id.to_string(),
+        deliveries: webhooks.deliveries(id),
+    }))
+}
+
+pub(crate) async fn webhooks_test_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<(StatusCode, Json<WebhookTestResponse>), (StatusCode, String)> {
+    require_admin(&principal)?;
+    let delivery_id = dispatcher(&state)?
+        .send_test(parse_endpoint_id(&id)?)
+        .await
+        .map_err(webhook_error)?;
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(WebhookTestResponse {
+            delivery_id: delivery_id.to_string(),
+        }),
+    ))
+}
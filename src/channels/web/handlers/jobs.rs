@@ -1,30 +1,55 @@
-//! Job lifecycle and sandbox file handlers.
+//! Job lifecycle and job artifact handlers.
 
+use std::convert::Infallible;
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::{
     Json, Router,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode, header},
+    response::{
+        IntoResponse,
+        sse::{Event, KeepAlive, Sse},
+    },
     routing::{get, post},
 };
 use serde::Deserialize;
 use uuid::Uuid;
 
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::handlers::helpers::matter::require_matter_access;
 use crate::channels::web::state::GatewayState;
 use crate::channels::web::types::*;
+use crate::db::{
+    AuditSeverity, CreateDocumentVersionParams, Database, DocumentReadinessState,
+    JobArtifactRecord, MatterDocumentCategory, MatterMemberRole, UpsertMatterDocumentParams,
+};
+use crate::orchestrator::artifacts::clean_artifact_name;
+use crate::orchestrator::job_manager::{ContainerHandle, JobAdmission, JobPriority};
 
 pub fn routes() -> Router<Arc<GatewayState>> {
     Router::new()
         .route("/api/jobs", get(jobs_list_handler))
         .route("/api/jobs/summary", get(jobs_summary_handler))
+        .route("/api/jobs/queue", get(jobs_queue_handler))
+        .route("/api/jobs/dead-letter", get(jobs_dead_letter_handler))
+        .route(
+            "/api/jobs/estimation-accuracy",
+            get(jobs_estimation_accuracy_handler),
+        )
         .route("/api/jobs/{id}", get(jobs_detail_handler))
         .route("/api/jobs/{id}/cancel", post(jobs_cancel_handler))
         .route("/api/jobs/{id}/restart", post(jobs_restart_handler))
+        .route("/api/jobs/{id}/requeue", post(jobs_requeue_handler))
         .route("/api/jobs/{id}/prompt", post(jobs_prompt_handler))
         .route("/api/jobs/{id}/events", get(jobs_events_handler))
-        .route("/api/jobs/{id}/files/list", get(job_files_list_handler))
-        .route("/api/jobs/{id}/files/read", get(job_files_read_handler))
+        .route("/api/jobs/{id}/logs/stream", get(jobs_logs_stream_handler))
+        .route("/api/jobs/{id}/artifacts", get(jobs_artifacts_list_handler))
+        .route(
+            "/api/jobs/{id}/artifacts/{*name}",
+            get(jobs_artifact_download_handler).post(jobs_artifact_promote_handler),
+        )
 }
 
 async fn jobs_list_handler(
@@ -45,21 +70,7 @@ async fn jobs_list_handler(
     let mut jobs: Vec<JobInfo> = sandbox_jobs
         .iter()
         .filter(|j| j.user_id == state.user_id)
-        .map(|j| {
-            let ui_state = match j.status.as_str() {
-                "creating" => "pending",
-                "running" => "in_progress",
-                s => s,
-            };
-            JobInfo {
-                id: j.id,
-                title: j.task.clone(),
-                state: ui_state.to_string(),
-                user_id: j.user_id.clone(),
-                created_at: j.created_at.to_rfc3339(),
-                started_at: j.started_at.map(|dt| dt.to_rfc3339()),
-            }
-        })
+        .map(crate::channels::web::server::sandbox_job_to_info)
         .collect();
 
     // Most recent first.
@@ -91,6 +102,112 @@ async fn jobs_summary_handler(
     }))
 }
 
+fn queue_entry_info(handle: &ContainerHandle, position: Option<usize>) -> JobQueueEntryInfo {
+    JobQueueEntryInfo {
+        job_id: handle.job_id,
+        title: handle.task_description.clone(),
+        state: handle.state.to_string(),
+        mode: handle.mode.as_str().to_string(),
+        priority: handle.priority.as_str().to_string(),
+        position,
+        created_at: handle.created_at.to_rfc3339(),
+        started_at: handle.started_at.map(|t| t.to_rfc3339()),
+        queued_at: handle.queued_at.map(|t| t.to_rfc3339()),
+        preemptions: handle.preemptions,
+        attempt: handle.attempt,
+        retry_at: handle.retry_at.map(|t| t.to_rfc3339()),
+    }
+}
+
+/// Running and waiting sandbox jobs. Totals cover every user so a caller
+/// can see why their job waits; entries are limited to their own jobs.
+async fn jobs_queue_handler(
+    State(state): State<Arc<GatewayState>>,
+) -> Result<Json<JobQueueResponse>, (StatusCode, String)> {
+    let jm = state.job_manager.as_ref().ok_or((
+        StatusCode::NOT_IMPLEMENTED,
+        "Sandbox not enabled".to_string(),
+    ))?;
+
+    let snapshot = jm.queue_snapshot().await;
+    let user_active = snapshot
+        .active
+        .iter()
+        .filter(|h| h.user_id == state.user_id)
+        .count();
+    Ok(Json(JobQueueResponse {
+        max_concurrent_jobs: snapshot.max_concurrent_jobs,
+        max_jobs_per_user: snapshot.max_jobs_per_user,
+        active_total: snapshot.active.len(),
+        queued_total: snapshot.queued.len(),
+        user_active,
+        active: snapshot
+            .active
+            .iter()
+            .filter(|h| h.user_id == state.user_id)
+            .map(|h| queue_entry_info(h, None))
+            .collect(),
+        queued: snapshot
+            .queued
+            .iter()
+            .enumerate()
+            .filter(|(_, h)| h.user_id == state.user_id)
+            .map(|(i, h)| queue_entry_info(h, Some(i + 1)))
+            .collect(),
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct EstimationAccuracyQuery {
+    pub(crate) category: Option<String>,
+}
+
+fn estimation_metric_info(metric: &crate::estimation::MetricForecast) -> EstimationMetricInfo {
+    EstimationMetricInfo {
+        raw_error_rate: metric.raw_error_rate,
+        corrected_error_rate: metric.corrected_error_rate,
+        slope: metric.fit.map(|fit| fit.slope),
+        intercept: metric.fit.map(|fit| fit.intercept),
+        r_squared: metric.fit.map(|fit| fit.r_squared),
+    }
+}
+
+/// Per-category accuracy of plan estimates against recorded actuals, with
+/// the correction the worker applies to new plans.
+pub(crate) async fn jobs_estimation_accuracy_handler(
+    State(state): State<Arc<GatewayState>>,
+    Query(query): Query<EstimationAccuracyQuery>,
+) -> Result<Json<EstimationAccuracyResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let category = query
+        .category
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty());
+    let samples = store
+        .list_estimation_samples(category, crate::estimation::FORECAST_HISTORY_LIMIT)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let forecaster = crate::estimation::EstimationForecaster::fit(&samples);
+    let categories = forecaster
+        .forecasts()
+        .into_iter()
+        .map(|forecast| EstimationCategoryAccuracyInfo {
+            category: forecast.category.clone(),
+            sample_count: forecast.sample_count,
+            cost: estimation_metric_info(&forecast.cost),
+            time: estimation_metric_info(&forecast.time),
+        })
+        .collect();
+    Ok(Json(EstimationAccuracyResponse {
+        min_samples: crate::estimation::MIN_FORECAST_SAMPLES,
+        categories,
+    }))
+}
+
 async fn jobs_detail_handler(
     State(state): State<Arc<GatewayState>>,
     Path(id): Path<String>,
@@ -177,7 +294,7 @@ async fn jobs_cancel_handler(
         if job.user_id != state.user_id {
             return Err((StatusCode::NOT_FOUND, "Job not found".to_string()));
         }
-        if job.status == "running" || job.status == "creating" {
+        if matches!(job.status.as_str(), "running" | "creating" | "queued") {
             // Stop the container if we have a job manager.
             if let Some(ref jm) = state.job_manager
                 && let Err(e) = jm.stop_job(job_id).await
@@ -209,6 +326,82 @@ async fn jobs_restart_handler(
     State(state): State<Arc<GatewayState>>,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let (old_job_id, new_job_id, queue_position) =
+        resubmit_sandbox_job(&state, &id, "restart", &["interrupted", "failed"]).await?;
+
+    Ok(Json(serde_json::json!({
+        "status": "restarted",
+        "old_job_id": old_job_id,
+        "new_job_id": new_job_id,
+        "queue_position": queue_position,
+    })))
+}
+
+/// Sandbox jobs that used up their retry attempts, most recent first.
+pub(crate) async fn jobs_dead_letter_handler(
+    State(state): State<Arc<GatewayState>>,
+) -> Result<Json<DeadLetterJobsResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+
+    let mut jobs: Vec<DeadLetterJobInfo> = store
+        .list_sandbox_jobs_for_user(&state.user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .into_iter()
+        .filter(|j| j.status == "dead_letter")
+        .map(|j| DeadLetterJobInfo {
+            id: j.id,
+            title: j.task,
+            failure_reason: j.failure_reason,
+            created_at: j.created_at.to_rfc3339(),
+            failed_at: j.completed_at.map(|t| t.to_rfc3339()),
+        })
+        .collect();
+    jobs.sort_by(|a, b| b.failed_at.cmp(&a.failed_at));
+
+    Ok(Json(DeadLetterJobsResponse { jobs }))
+}
+
+/// Resubmit a dead-lettered job with a fresh set of attempts. The original
+/// record moves to `requeued` so it leaves the dead-letter list.
+async fn jobs_requeue_handler(
+    State(state): State<Arc<GatewayState>>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let (old_job_id, new_job_id, queue_position) =
+        resubmit_sandbox_job(&state, &id, "requeue", &["dead_letter"]).await?;
+
+    if let Some(ref store) = state.store
+        && let Err(e) = store
+            .update_sandbox_job_status(old_job_id, "requeued", None, None, None, None)
+            .await
+    {
+        tracing::warn!(job_id = %old_job_id, error = %e, "Failed to mark dead-letter job requeued");
+    }
+
+    Ok(Json(serde_json::json!({
+        "status": "requeued",
+        "old_job_id": old_job_id,
+        "new_job_id": new_job_id,
+        "queue_position": queue_position,
+    })))
+}
+
+/// Create a new sandbox job with the task, project dir, mode, priority, and
+/// credential grants of a finished one in one of `allowed_statuses`
+/// (`action` names the operation in the conflict message).
+///
+/// Returns the old and new job IDs and the new job's queue position (if it
+/// did not start right away).
+async fn resubmit_sandbox_job(
+    state: &GatewayState,
+    id: &str,
+    action: &str,
+    allowed_statuses: &[&str],
+) -> Result<(Uuid, Uuid, Option<usize>), (StatusCode, String)> {
     let store = state.store.as_ref().ok_or((
         StatusCode::SERVICE_UNAVAILABLE,
         "Database not available".to_string(),
@@ -218,8 +411,8 @@ async fn jobs_restart_handler(
         "Sandbox not enabled".to_string(),
     ))?;
 
-    let old_job_id = Uuid::parse_str(&id)
-        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid job ID".to_string()))?;
+    let old_job_id =
+        Uuid::parse_str(id).map_err(|_| (StatusCode::BAD_REQUEST, "Invalid job ID".to_string()))?;
 
     let old_job = store
         .get_sandbox_job(old_job_id)
@@ -232,10 +425,10 @@ async fn jobs_restart_handler(
         return Err((StatusCode::NOT_FOUND, "Job not found".to_string()));
     }
 
-    if old_job.status != "interrupted" && old_job.status != "failed" {
+    if !allowed_statuses.contains(&old_job.status.as_str()) {
         return Err((
             StatusCode::CONFLICT,
-            format!("Cannot restart job in state '{}'", old_job.status),
+            format!("Cannot {} job in state '{}'", action, old_job.status),
         ));
     }
 
@@ -280,13 +473,30 @@ async fn jobs_restart_handler(
             vec![]
         });
 
+    // Keep the original job's place in the queue ordering.
+    let priority = store
+        .get_sandbox_job_priority(old_job_id)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|p| JobPriority::parse(&p))
+        .unwrap_or_default();
+    if priority != JobPriority::Normal {
+        store
+            .update_sandbox_job_priority(new_job_id, priority.as_str())
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    }
+
     let project_dir = std::path::PathBuf::from(&old_job.project_dir);
-    let _token = jm
+    let admission = jm
         .create_job(
             new_job_id,
+            &old_job.user_id,
             &old_job.task,
             Some(project_dir),
             mode,
+            priority,
             credential_grants,
         )
         .await
@@ -297,16 +507,24 @@ async fn jobs_restart_handler(
             )
         })?;
 
-    store
-        .update_sandbox_job_status(new_job_id, "running", None, None, Some(now), None)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let queue_position = match admission {
+        JobAdmission::Started => {
+            store
+                .update_sandbox_job_status(new_job_id, "running", None, None, Some(now), None)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            None
+        }
+        JobAdmission::Queued { .. } | JobAdmission::Retrying { .. } => {
+            store
+                .update_sandbox_job_status(new_job_id, "queued", None, None, None, None)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            jm.queue_position(new_job_id).await
+        }
+    };
 
-    Ok(Json(serde_json::json!({
-        "status": "restarted",
-        "old_job_id": old_job_id,
-        "new_job_id": new_job_id,
-    })))
+    Ok((old_job_id, new_job_id, queue_position))
 }
 
 // --- Claude Code prompt and events handlers ---
@@ -407,25 +625,128 @@ async fn jobs_events_handler(
     })))
 }
 
-// --- Project file handlers for sandbox jobs ---
+// --- Live job log stream ---
+
+/// Events fetched per database read while tailing a job.
+const LOG_STREAM_BATCH: i64 = 200;
+/// Delay between reads once the stream has caught up.
+const LOG_STREAM_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
-#[derive(Deserialize)]
-struct FilePathQuery {
-    path: Option<String>,
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct JobLogStreamQuery {
+    /// Resume point for clients that cannot set `Last-Event-ID`.
+    pub(crate) last_event_id: Option<i64>,
 }
 
-async fn job_files_list_handler(
+/// Tail a job's events and container output as SSE.
+///
+/// Each `job_event` carries the persisted event id, so a reconnecting
+/// `EventSource` resumes where it left off via `Last-Event-ID`. The stream
+/// closes with a `done` event once the job has finished and everything has
+/// been sent.
+pub(crate) async fn jobs_logs_stream_handler(
     State(state): State<Arc<GatewayState>>,
     Path(id): Path<String>,
-    Query(query): Query<FilePathQuery>,
-) -> Result<Json<ProjectFilesResponse>, (StatusCode, String)> {
+    Query(query): Query<JobLogStreamQuery>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let job = load_owned_sandbox_job(state.as_ref(), &id).await?;
+    let store = Arc::clone(state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?);
+    let after = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .or(query.last_event_id)
+        .unwrap_or(0);
+
+    let (tx, rx) = tokio::sync::mpsc::channel(64);
+    tokio::spawn(tail_job_events(store, job.id, after, tx));
+
+    Ok((
+        [("X-Accel-Buffering", "no"), ("Cache-Control", "no-cache")],
+        Sse::new(tokio_stream::wrappers::ReceiverStream::new(rx))
+            .keep_alive(KeepAlive::new().interval(Duration::from_secs(30)).text("")),
+    ))
+}
+
+async fn tail_job_events(
+    store: Arc<dyn Database>,
+    job_id: Uuid,
+    mut after: i64,
+    tx: tokio::sync::mpsc::Sender<Result<Event, Infallible>>,
+) {
+    loop {
+        // Read the status before the events so a job that finishes in
+        // between still has its last events sent before `done`.
+        let status = match store.get_sandbox_job(job_id).await {
+            Ok(Some(job)) => job.status,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!(job_id = %job_id, error = %e, "Job log stream lost its job");
+                return;
+            }
+        };
+        let events = match store
+            .list_job_events_after(job_id, after, LOG_STREAM_BATCH)
+            .await
+        {
+            Ok(events) => events,
+            Err(e) => {
+                tracing::warn!(job_id = %job_id, error = %e, "Job log stream read failed");
+                return;
+            }
+        };
+        let caught_up = (events.len() as i64) < LOG_STREAM_BATCH;
+        for event in events {
+            after = event.id;
+            let data = serde_json::json!({
+                "id": event.id,
+                "event_type": event.event_type,
+                "data": event.data,
+                "created_at": event.created_at.to_rfc3339(),
+            });
+            let sse = Event::default()
+                .id(event.id.to_string())
+                .event("job_event")
+                .data(data.to_string());
+            if tx.send(Ok(sse)).await.is_err() {
+                return;
+            }
+        }
+        if !caught_up {
+            continue;
+        }
+        if !matches!(status.as_str(), "creating" | "running" | "queued") {
+            let done = serde_json::json!({ "status": status });
+            let _ = tx
+                .send(Ok(Event::default().event("done").data(done.to_string())))
+                .await;
+            return;
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(LOG_STREAM_POLL_INTERVAL) => {}
+            _ = tx.closed() => return,
+        }
+    }
+}
+
+// --- Job artifact handlers ---
+
+/// Load a sandbox job, hiding jobs that belong to other users.
+async fn load_owned_sandbox_job(
+    state: &GatewayState,
+    id: &str,
+) -> Result<crate::history::SandboxJobRecord, (StatusCode, String)> {
     let store = state.store.as_ref().ok_or((
         StatusCode::SERVICE_UNAVAILABLE,
         "Database not available".to_string(),
     ))?;
 
-    let job_id = Uuid::parse_str(&id)
-        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid job ID".to_string()))?;
+    let job_id =
+        Uuid::parse_str(id).map_err(|_| (StatusCode::BAD_REQUEST, "Invalid job ID".to_string()))?;
 
     let job = store
         .get_sandbox_job(job_id)
@@ -437,100 +758,195 @@ async fn job_files_list_handler(
     if job.user_id != state.user_id {
         return Err((StatusCode::NOT_FOUND, "Job not found".to_string()));
     }
+    Ok(job)
+}
 
-    let base = std::path::PathBuf::from(&job.project_dir);
-    let rel_path = query.path.as_deref().unwrap_or("");
-    let target = base.join(rel_path);
-
-    // Path traversal guard.
-    let canonical = target
-        .canonicalize()
-        .map_err(|_| (StatusCode::NOT_FOUND, "Path not found".to_string()))?;
-    let base_canonical = base
-        .canonicalize()
-        .map_err(|_| (StatusCode::NOT_FOUND, "Project dir not found".to_string()))?;
-    if !canonical.starts_with(&base_canonical) {
-        return Err((StatusCode::FORBIDDEN, "Forbidden".to_string()));
-    }
-
-    let mut entries = Vec::new();
-    let mut read_dir = tokio::fs::read_dir(&canonical)
+/// Look up a registered artifact by its client-supplied name.
+async fn load_job_artifact(
+    state: &GatewayState,
+    job_id: Uuid,
+    raw_name: &str,
+) -> Result<JobArtifactRecord, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let name = clean_artifact_name(raw_name)
+        .ok_or((StatusCode::BAD_REQUEST, "Invalid artifact name".to_string()))?;
+    store
+        .get_job_artifact(job_id, &name)
         .await
-        .map_err(|_| (StatusCode::NOT_FOUND, "Cannot read directory".to_string()))?;
-
-    while let Ok(Some(entry)) = read_dir.next_entry().await {
-        let name = entry.file_name().to_string_lossy().to_string();
-        let is_dir = entry
-            .file_type()
-            .await
-            .map(|ft| ft.is_dir())
-            .unwrap_or(false);
-        let rel = if rel_path.is_empty() {
-            name.clone()
-        } else {
-            format!("{}/{}", rel_path, name)
-        };
-        entries.push(ProjectFileEntry {
-            name,
-            path: rel,
-            is_dir,
-        });
-    }
-
-    entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
-
-    Ok(Json(ProjectFilesResponse { entries }))
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Artifact not found".to_string()))
 }
 
-async fn job_files_read_handler(
+pub(crate) async fn jobs_artifacts_list_handler(
     State(state): State<Arc<GatewayState>>,
     Path(id): Path<String>,
-    Query(query): Query<FilePathQuery>,
-) -> Result<Json<ProjectFileReadResponse>, (StatusCode, String)> {
+) -> Result<Json<JobArtifactsResponse>, (StatusCode, String)> {
+    let job = load_owned_sandbox_job(state.as_ref(), &id).await?;
     let store = state.store.as_ref().ok_or((
         StatusCode::SERVICE_UNAVAILABLE,
         "Database not available".to_string(),
     ))?;
-
-    let job_id = Uuid::parse_str(&id)
-        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid job ID".to_string()))?;
-
-    let job = store
-        .get_sandbox_job(job_id)
+    let artifacts = store
+        .list_job_artifacts(job.id)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((StatusCode::NOT_FOUND, "Job not found".to_string()))?;
+        .into_iter()
+        .map(|a| JobArtifactInfo {
+            name: a.name,
+            size_bytes: a.size_bytes,
+            content_type: a.content_type,
+            created_at: a.created_at.to_rfc3339(),
+            promoted_to: a.promoted_to,
+        })
+        .collect();
+    Ok(Json(JobArtifactsResponse {
+        job_id: job.id,
+        artifacts,
+    }))
+}
 
-    // Verify user owns this job.
-    if job.user_id != state.user_id {
-        return Err((StatusCode::NOT_FOUND, "Job not found".to_string()));
-    }
+pub(crate) async fn jobs_artifact_download_handler(
+    State(state): State<Arc<GatewayState>>,
+    Path((id, name)): Path<(String, String)>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let job = load_owned_sandbox_job(state.as_ref(), &id).await?;
+    let artifact = load_job_artifact(state.as_ref(), job.id, &name).await?;
+    let bytes = tokio::fs::read(&artifact.storage_path).await.map_err(|_| {
+        (
+            StatusCode::GONE,
+            "Artifact file is no longer stored".to_string(),
+        )
+    })?;
+
+    let file_name = artifact
+        .name
+        .rsplit('/')
+        .next()
+        .unwrap_or("artifact")
+        .replace('"', "");
+    Ok((
+        [
+            (header::CONTENT_TYPE, artifact.content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name),
+            ),
+        ],
+        bytes,
+    ))
+}
 
-    let path = query.path.as_deref().ok_or((
-        StatusCode::BAD_REQUEST,
-        "path parameter required".to_string(),
+/// Copy a text artifact into a matter's workspace as an internal draft.
+pub(crate) async fn jobs_artifact_promote_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path((id, name)): Path<(String, String)>,
+    Json(req): Json<PromoteJobArtifactRequest>,
+) -> Result<Json<PromoteJobArtifactResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
     ))?;
-
-    let base = std::path::PathBuf::from(&job.project_dir);
-    let file_path = base.join(path);
-
-    let canonical = file_path
-        .canonicalize()
-        .map_err(|_| (StatusCode::NOT_FOUND, "File not found".to_string()))?;
-    let base_canonical = base
-        .canonicalize()
-        .map_err(|_| (StatusCode::NOT_FOUND, "Project dir not found".to_string()))?;
-    if !canonical.starts_with(&base_canonical) {
-        return Err((StatusCode::FORBIDDEN, "Forbidden".to_string()));
-    }
-
-    let content = tokio::fs::read_to_string(&canonical)
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let job = load_owned_sandbox_job(state.as_ref(), &id).await?;
+    let artifact = load_job_artifact(state.as_ref(), job.id, &name).await?;
+
+    let matter_root = crate::channels::web::server::matter_root_for_gateway(state.as_ref());
+    let matter_id = crate::channels::web::server::ensure_existing_matter_for_route(
+        workspace.as_ref(),
+        &matter_root,
+        &req.matter_id,
+    )
+    .await?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Collaborator,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    crate::channels::web::server::ensure_matter_db_row_from_workspace(state.as_ref(), &matter_id)
+        .await?;
+
+    let bytes = tokio::fs::read(&artifact.storage_path).await.map_err(|_| {
+        (
+            StatusCode::GONE,
+            "Artifact file is no longer stored".to_string(),
+        )
+    })?;
+    let content = String::from_utf8(bytes).map_err(|_| {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Only text artifacts can be promoted into a matter".to_string(),
+        )
+    })?;
+
+    let job_short: String = job.id.to_string().chars().take(8).collect();
+    let path = format!(
+        "{}/artifacts/job-{}/{}",
+        crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), &matter_id),
+        job_short,
+        artifact.name
+    );
+    let written = workspace
+        .write(&path, &content)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let document = store
+        .upsert_matter_document(
+            &state.user_id,
+            &matter_id,
+            &UpsertMatterDocumentParams {
+                memory_document_id: written.id,
+                path: written.path.clone(),
+                display_name: format!("Job artifact: {}", artifact.name),
+                category: MatterDocumentCategory::Internal,
+                readiness_state: Some(DocumentReadinessState::Draft),
+            },
+        )
         .await
-        .map_err(|_| (StatusCode::NOT_FOUND, "Cannot read file".to_string()))?;
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    store
+        .create_document_version(
+            &state.user_id,
+            &CreateDocumentVersionParams {
+                matter_document_id: document.id,
+                label: "initial".to_string(),
+                memory_document_id: written.id,
+            },
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    store
+        .mark_job_artifact_promoted(job.id, &artifact.name, &written.path)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    Ok(Json(ProjectFileReadResponse {
-        path: path.to_string(),
-        content,
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "job_artifact_promoted",
+        principal.user_id.as_str(),
+        Some(matter_id.as_str()),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "job_id": job.id.to_string(),
+            "artifact": artifact.name,
+            "path": written.path,
+        }),
+    )
+    .await;
+
+    Ok(Json(PromoteJobArtifactResponse {
+        matter_id,
+        document: crate::channels::web::server::matter_document_record_to_info(document),
     }))
 }
 
@@ -4,11 +4,14 @@ use std::sync::Arc;
 
 use axum::{
     Json, Router,
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::StatusCode,
-    routing::{get, post},
+    routing::{get, post, put},
 };
 use chrono::Utc;
+use rust_decimal::Decimal;
+
+use crate::agent::cost_guard::{CostGroupBy, group_cost_entries, month_start, to_cents};
 
 use crate::channels::web::state::GatewayState;
 use crate::channels::web::types::*;
@@ -19,15 +22,26 @@ pub fn routes() -> Router<Arc<GatewayState>> {
     Router::new()
         .route("/api/legal/audit", get(legal_audit_list_handler))
         .route("/api/legal/court-rules", get(legal_court_rules_handler))
+        .route("/api/legal/costs", get(legal_costs_handler))
+        .route(
+            "/api/legal/immigration-forms",
+            get(legal_immigration_forms_handler),
+        )
+        .route("/api/legal/ip-rules", get(legal_ip_rules_handler))
+        .route(
+            "/api/legal/costs/caps/{matter_id}",
+            put(legal_cost_cap_put_handler),
+        )
         .route("/api/compliance/status", get(compliance_status_handler))
         .route("/api/compliance/letter", post(compliance_letter_handler))
 }
 
-fn court_rule_to_info(rule: &crate::legal::calendar::CourtRule) -> CourtRuleInfo {
+pub(crate) fn court_rule_to_info(rule: &crate::legal::calendar::CourtRule) -> CourtRuleInfo {
     CourtRuleInfo {
         id: rule.id.clone(),
         citation: rule.citation.clone(),
         deadline_type: rule.deadline_type.as_str().to_string(),
+        offset_months: rule.offset_months,
         offset_days: rule.offset_days,
         court_days: rule.court_days,
         version: rule.version.clone(),
@@ -43,6 +57,69 @@ pub(crate) async fn legal_court_rules_handler()
     Ok(Json(CourtRulesResponse { rules: payload }))
 }
 
+pub(crate) async fn legal_ip_rules_handler() -> Result<Json<IpRulesResponse>, (StatusCode, String)>
+{
+    let mut offices = Vec::with_capacity(crate::legal::ip_docket::IP_OFFICES.len());
+    for office in crate::legal::ip_docket::IP_OFFICES {
+        let mut rules = Vec::new();
+        for meta in crate::legal::ip_docket::ip_rules()
+            .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?
+            .iter()
+            .filter(|meta| meta.jurisdiction == office.code)
+        {
+            if let Some(rule) = crate::legal::calendar::get_court_rule(&meta.id)
+                .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?
+            {
+                rules.push(IpRuleInfo {
+                    rule: court_rule_to_info(&rule),
+                    right: meta.right.as_str().to_string(),
+                    trigger: meta.trigger.as_str().to_string(),
+                    title: meta.title.clone(),
+                    grace_for: meta.grace_for.clone(),
+                });
+            }
+        }
+        offices.push(IpOfficeInfo {
+            code: office.code.to_string(),
+            name: office.name.to_string(),
+            aliases: office
+                .aliases
+                .iter()
+                .map(|alias| alias.to_string())
+                .collect(),
+            rules,
+        });
+    }
+    Ok(Json(IpRulesResponse { offices }))
+}
+
+pub(crate) async fn legal_immigration_forms_handler()
+-> Result<Json<ImmigrationFormsResponse>, (StatusCode, String)> {
+    let forms = crate::legal::immigration::immigration_forms()
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?;
+    let mut payload = Vec::with_capacity(forms.len());
+    for form in forms {
+        let mut deadline_presets = Vec::with_capacity(form.deadline_presets.len());
+        for rule_id in &form.deadline_presets {
+            if let Some(rule) = crate::legal::calendar::get_court_rule(rule_id)
+                .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?
+            {
+                deadline_presets.push(court_rule_to_info(&rule));
+            }
+        }
+        payload.push(ImmigrationFormInfo {
+            id: form.id.clone(),
+            title: form.title.clone(),
+            agency: form.agency.clone(),
+            edition: form.edition.clone(),
+            field_count: form.fields.len(),
+            required_count: form.fields.iter().filter(|field| field.required).count(),
+            deadline_presets,
+        });
+    }
+    Ok(Json(ImmigrationFormsResponse { forms: payload }))
+}
+
 pub(crate) async fn legal_audit_list_handler(
     State(state): State<Arc<GatewayState>>,
     Query(query): Query<crate::channels::web::server::LegalAuditQuery>,
@@ -135,6 +212,171 @@ pub(crate) async fn legal_audit_list_handler(
     }))
 }
 
+fn format_usd(amount: Decimal) -> String {
+    format!("{:.4}", amount)
+}
+
+fn parse_cost_group_by(raw: Option<&str>) -> Result<CostGroupBy, (StatusCode, String)> {
+    match raw.map(str::trim).unwrap_or_default() {
+        "" | "matter" => Ok(CostGroupBy::Matter),
+        "client" => Ok(CostGroupBy::Client),
+        "source" => Ok(CostGroupBy::Source),
+        "day" => Ok(CostGroupBy::Day),
+        _ => Err((
+            StatusCode::BAD_REQUEST,
+            "'group_by' must be one of: matter, client, source, day".to_string(),
+        )),
+    }
+}
+
+pub(crate) async fn legal_costs_handler(
+    State(state): State<Arc<GatewayState>>,
+    Query(query): Query<LegalCostsQuery>,
+) -> Result<Json<LegalCostsResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let group_by = parse_cost_group_by(query.group_by.as_deref())?;
+    let since = crate::channels::web::server::parse_utc_query_ts("since", query.since.as_deref())?;
+    let until = crate::channels::web::server::parse_utc_query_ts("until", query.until.as_deref())?;
+    if let (Some(since), Some(until)) = (since, until)
+        && since > until
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'since' must be earlier than or equal to 'until'".to_string(),
+        ));
+    }
+
+    let entries = store
+        .list_cost_entries(&state.user_id, since, until)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let total: Decimal = entries.iter().map(|entry| entry.cost).sum();
+    let mut groups = group_cost_entries(&entries, group_by)
+        .into_iter()
+        .map(|group| LegalCostGroupInfo {
+            key: group.key,
+            cost_usd: format_usd(group.cost),
+            input_tokens: group.input_tokens,
+            output_tokens: group.output_tokens,
+            entries: group.entries,
+            monthly_cap_cents: None,
+            month_to_date_usd: None,
+            cap_exceeded: None,
+        })
+        .collect::<Vec<_>>();
+
+    if group_by == CostGroupBy::Matter {
+        let caps = store
+            .list_matter_spend_caps(&state.user_id)
+            .await
+            .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+        let month = month_start(Utc::now());
+        for cap in caps {
+            let spent = store
+                .matter_cost_since(&state.user_id, &cap.matter_id, month)
+                .await
+                .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+            let index = match groups
+                .iter()
+                .position(|group| group.key.as_deref() == Some(cap.matter_id.as_str()))
+            {
+                Some(index) => index,
+                None => {
+                    // Capped matters are listed even without spend in range.
+                    groups.push(LegalCostGroupInfo {
+                        key: Some(cap.matter_id.clone()),
+                        cost_usd: format_usd(Decimal::ZERO),
+                        input_tokens: 0,
+                        output_tokens: 0,
+                        entries: 0,
+                        monthly_cap_cents: None,
+                        month_to_date_usd: None,
+                        cap_exceeded: None,
+                    });
+                    groups.len() - 1
+                }
+            };
+            let group = &mut groups[index];
+            group.monthly_cap_cents = Some(cap.monthly_cap_cents);
+            group.month_to_date_usd = Some(format_usd(spent));
+            group.cap_exceeded = Some(to_cents(spent) >= cap.monthly_cap_cents.max(0) as u64);
+        }
+    }
+
+    Ok(Json(LegalCostsResponse {
+        group_by: match group_by {
+            CostGroupBy::Matter => "matter",
+            CostGroupBy::Client => "client",
+            CostGroupBy::Source => "source",
+            CostGroupBy::Day => "day",
+        }
+        .to_string(),
+        total_usd: format_usd(total),
+        groups,
+    }))
+}
+
+pub(crate) async fn legal_cost_cap_put_handler(
+    State(state): State<Arc<GatewayState>>,
+    Path(matter_id): Path<String>,
+    Json(req): Json<MatterSpendCapRequest>,
+) -> Result<Json<MatterSpendCapResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(&matter_id)?;
+    if store
+        .get_matter_db(&state.user_id, &matter_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .is_none()
+    {
+        return Err((StatusCode::NOT_FOUND, "Matter not found".to_string()));
+    }
+    let cap = req
+        .monthly_cap_cents
+        .map(i64::try_from)
+        .transpose()
+        .map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                "'monthly_cap_cents' is too large".to_string(),
+            )
+        })?;
+
+    store
+        .set_matter_spend_cap(&state.user_id, &matter_id, cap)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let spent = store
+        .matter_cost_since(&state.user_id, &matter_id, month_start(Utc::now()))
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "matter_spend_cap_updated",
+        state.user_id.as_str(),
+        Some(matter_id.as_str()),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "matter_id": matter_id.clone(),
+            "monthly_cap_cents": cap,
+        }),
+    )
+    .await;
+
+    Ok(Json(MatterSpendCapResponse {
+        matter_id,
+        monthly_cap_cents: cap,
+        month_to_date_usd: format_usd(spent),
+    }))
+}
+
 fn compliance_status_level(level: crate::compliance::ComplianceState) -> ComplianceStatusLevel {
     match level {
         crate::compliance::ComplianceState::Compliant => ComplianceStatusLevel::Compliant,
@@ -528,7 +770,8 @@ pub(crate) async fn compliance_letter_handler(
         ChatMessage::user(prompt),
     ])
     .with_temperature(0.1)
-    .with_max_tokens(1800);
+    .with_max_tokens(1800)
+    .with_task(crate::llm::LlmTask::Drafting);
 
     let completion = llm.complete(request).await.map_err(|err| {
         (
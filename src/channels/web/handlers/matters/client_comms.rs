@@ -0,0 +1,233 @@
+//! Client communication preference and consent handlers.
+//!
+//! `GET`/`PUT /api/clients/{id}/communication-preferences` read and replace a
+//! client's preferences; `POST /api/clients/{id}/communication-consents`
+//! appends a consent decision. The rules they feed are applied to every
+//! client-facing send by [`crate::legal::client_comms::clear_client_send`].
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+use uuid::Uuid;
+
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{
+    AuditSeverity, CommunicationChannel, CommunicationConsentStatus,
+    CreateClientCommunicationConsentParams, Database, UpsertClientCommunicationPreferencesParams,
+};
+use crate::legal::client_comms::{load_policy, validate_preferences};
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new()
+        .route(
+            "/api/clients/{id}/communication-preferences",
+            get(client_communications_get_handler).put(client_communications_put_handler),
+        )
+        .route(
+            "/api/clients/{id}/communication-consents",
+            post(client_consent_record_handler),
+        )
+}
+
+/// Parse the route id and make sure the client exists.
+async fn client_for_route<'a>(
+    state: &'a GatewayState,
+    raw_client_id: &str,
+) -> Result<(&'a Arc<dyn Database>, Uuid), (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let client_id = crate::channels::web::server::parse_uuid(raw_client_id, "id")?;
+    store
+        .get_client(&state.user_id, client_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Client not found".to_string()))?;
+    Ok((store, client_id))
+}
+
+fn parse_channel(raw: &str) -> Result<CommunicationChannel, (StatusCode, String)> {
+    CommunicationChannel::from_db_value(raw.trim().to_ascii_lowercase().as_str()).ok_or((
+        StatusCode::BAD_REQUEST,
+        format!(
+            "Unknown channel '{}' (expected email, sms, phone, or mail)",
+            raw.trim()
+        ),
+    ))
+}
+
+async fn client_communications_response(
+    state: &GatewayState,
+    store: &Arc<dyn Database>,
+    client_id: Uuid,
+) -> Result<ClientCommunicationsResponse, (StatusCode, String)> {
+    let policy = load_policy(store.as_ref(), &state.user_id, client_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let consents = CommunicationChannel::ALL
+        .iter()
+        .filter_map(|channel| policy.current_consent(*channel).cloned())
+        .map(crate::channels::web::server::client_communication_consent_record_to_info)
+        .collect();
+    Ok(ClientCommunicationsResponse {
+        client_id: client_id.to_string(),
+        preferences: policy
+            .preferences
+            .map(crate::channels::web::server::client_communication_preferences_record_to_info),
+        consents,
+        history: policy
+            .consents
+            .into_iter()
+            .map(crate::channels::web::server::client_communication_consent_record_to_info)
+            .collect(),
+    })
+}
+
+pub(crate) async fn client_communications_get_handler(
+    State(state): State<Arc<GatewayState>>,
+    Path(id): Path<String>,
+) -> Result<Json<ClientCommunicationsResponse>, (StatusCode, String)> {
+    let (store, client_id) = client_for_route(state.as_ref(), &id).await?;
+    Ok(Json(
+        client_communications_response(state.as_ref(), store, client_id).await?,
+    ))
+}
+
+pub(crate) async fn client_communications_put_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateClientCommunicationPreferencesRequest>,
+) -> Result<Json<ClientCommunicationsResponse>, (StatusCode, String)> {
+    let (store, client_id) = client_for_route(state.as_ref(), &id).await?;
+    let mut allowed_channels = Vec::new();
+    for raw in &req.allowed_channels {
+        let channel = parse_channel(raw)?;
+        if !allowed_channels.contains(&channel) {
+            allowed_channels.push(channel);
+        }
+    }
+    let timezone = crate::channels::web::server::parse_optional_matter_field(req.timezone);
+    let language = crate::channels::web::server::parse_optional_matter_field(req.language);
+    let disclaimers: Vec<String> = req
+        .disclaimers
+        .iter()
+        .map(|text| text.trim().to_string())
+        .collect();
+    validate_preferences(timezone.as_deref(), language.as_deref(), &disclaimers)
+        .map_err(|err| (StatusCode::BAD_REQUEST, err))?;
+
+    let saved = store
+        .upsert_client_communication_preferences(
+            &state.user_id,
+            client_id,
+            &UpsertClientCommunicationPreferencesParams {
+                allowed_channels,
+                quiet_hours: req.quiet_hours,
+                timezone,
+                language,
+                disclaimers,
+                updated_by: principal.user_id.clone(),
+            },
+        )
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "client_communication_preferences_updated",
+        &principal.user_id,
+        None,
+        AuditSeverity::Info,
+        serde_json::json!({
+            "client_id": client_id.to_string(),
+            "allowed_channels": saved
+                .allowed_channels
+                .iter()
+                .map(|channel| channel.as_str())
+                .collect::<Vec<_>>(),
+            "quiet_hours": saved.quiet_hours.len(),
+            "language": saved.language,
+            "disclaimers": saved.disclaimers.len(),
+        }),
+    )
+    .await;
+    Ok(Json(
+        client_communications_response(state.as_ref(), store, client_id).await?,
+    ))
+}
+
+pub(crate) async fn client_consent_record_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<RecordClientConsentRequest>,
+) -> Result<(StatusCode, Json<ClientCommunicationsResponse>), (StatusCode, String)> {
+    let (store, client_id) = client_for_route(state.as_ref(), &id).await?;
+    let channel = parse_channel(&req.channel)?;
+    let status =
+        CommunicationConsentStatus::from_db_value(req.status.trim().to_ascii_lowercase().as_str())
+            .ok_or((
+                StatusCode::BAD_REQUEST,
+                "'status' must be 'granted' or 'revoked'".to_string(),
+            ))?;
+    let source = crate::channels::web::server::parse_required_matter_field("source", &req.source)?;
+    let now = chrono::Utc::now();
+    let consented_at = match req
+        .consented_at
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        Some(raw) => crate::channels::web::server::parse_datetime_value("consented_at", raw)?,
+        None => now,
+    };
+    if consented_at > now {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "consented_at cannot be in the future".to_string(),
+        ));
+    }
+
+    let record = store
+        .create_client_communication_consent(
+            &state.user_id,
+            client_id,
+            &CreateClientCommunicationConsentParams {
+                channel,
+                status,
+                source,
+                consented_at,
+                recorded_by: principal.user_id.clone(),
+            },
+        )
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "client_communication_consent_recorded",
+        &principal.user_id,
+        None,
+        AuditSeverity::Info,
+        serde_json::json!({
+            "client_id": client_id.to_string(),
+            "consent_id": record.id.to_string(),
+            "channel": record.channel.as_str(),
+            "status": record.status.as_str(),
+            "source": record.source,
+            "consented_at": record.consented_at.to_rfc3339(),
+        }),
+    )
+    .await;
+    Ok((
+        StatusCode::CREATED,
+        Json(client_communications_response(state.as_ref(), store, client_id).await?),
+    ))
+}
@@ -0,0 +1,342 @@
+//! Matter closeout handlers.
+//!
+//! `GET /api/matters/{id}/close` reports whether a matter can be closed;
+//! `POST` runs the closeout: checks, closing letter, retention clock, and the
+//! move to `closed`.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::get,
+};
+use chrono::{SubsecRound, Utc};
+use uuid::Uuid;
+
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::handlers::helpers::matter::require_matter_access;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{
+    AuditSeverity, CreateMatterDeadlineParams, MatterDeadlineType, MatterMemberRole, MatterRecord,
+    MatterStatus, UpdateMatterParams,
+};
+use crate::legal::closeout::{
+    CLOSING_LETTER_TEMPLATE_BODY, CLOSING_LETTER_TEMPLATE_NAME, CloseoutCheck, CloseoutFacts,
+    DEFAULT_RETENTION_YEARS, MAX_RETENTION_YEARS, RETENTION_DEADLINE_TITLE,
+};
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new().route(
+        "/api/matters/{id}/close",
+        get(matter_closeout_status_handler).post(matter_close_handler),
+    )
+}
+
+async fn load_closeout_matter(
+    state: &GatewayState,
+    principal_user_id: &str,
+    raw_matter_id: &str,
+    role: MatterMemberRole,
+) -> Result<MatterRecord, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(raw_matter_id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        principal_user_id,
+        role,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    store
+        .get_matter_db(&state.user_id, &matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Matter not found".to_string()))
+}
+
+async fn closeout_checks_for_matter(
+    state: &GatewayState,
+    matter_id: &str,
+) -> Result<Vec<CloseoutCheck>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let summary = store
+        .matter_time_summary(&state.user_id, matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let open_deadlines = store
+        .list_matter_deadlines(&state.user_id, matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .into_iter()
+        .filter(|deadline| deadline.completed_at.is_none())
+        .map(|deadline| deadline.title)
+        .collect();
+    let trust_balance = store
+        .current_trust_balance(&state.user_id, matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(crate::legal::closeout::closeout_checks(&CloseoutFacts {
+        unbilled_hours: summary.unbilled_hours,
+        unbilled_expenses: summary.unbilled_expenses,
+        open_deadlines,
+        trust_balance,
+    }))
+}
+
+/// Retention period from the matter's `matter.yaml` policy, else the default.
+async fn policy_retention_years(state: &GatewayState, matter_id: &str) -> u32 {
+    let matter_root = crate::channels::web::server::matter_root_for_gateway(state);
+    crate::channels::web::server::read_workspace_matter_metadata_optional(
+        state.workspace.as_ref(),
+        &matter_root,
+        matter_id,
+    )
+    .await
+    .and_then(|metadata| crate::legal::closeout::retention_years_from_policy(&metadata.retention))
+    .unwrap_or(DEFAULT_RETENTION_YEARS)
+}
+
+pub(crate) async fn matter_closeout_status_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<MatterCloseoutStatusResponse>, (StatusCode, String)> {
+    let matter = load_closeout_matter(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Viewer,
+    )
+    .await?;
+    let checks = closeout_checks_for_matter(state.as_ref(), &matter.matter_id).await?;
+    Ok(Json(MatterCloseoutStatusResponse {
+        ready: matter.status != MatterStatus::Closed && checks.iter().all(|check| check.passed),
+        status: matter.status.as_str().to_string(),
+        checks,
+        retention_years: policy_retention_years(state.as_ref(), &matter.matter_id).await,
+        matter_id: matter.matter_id,
+    }))
+}
+
+pub(crate) async fn matter_close_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<CloseMatterRequest>,
+) -> Result<Json<CloseMatterResponse>, (StatusCode, String)> {
+    let matter = load_closeout_matter(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let matter_id = matter.matter_id.clone();
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    if matter.status == MatterStatus::Closed {
+        return Err((
+            StatusCode::CONFLICT,
+            format!("Matter '{matter_id}' is already closed"),
+        ));
+    }
+    let note = crate::channels::web::server::parse_optional_matter_field(req.note);
+    crate::channels::web::server::validate_optional_matter_field_length("note", &note)?;
+    let retention_years = match req.retention_years {
+        Some(years) if !(1..=MAX_RETENTION_YEARS).contains(&years) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("'retention_years' must be between 1 and {MAX_RETENTION_YEARS}"),
+            ));
+        }
+        Some(years) => years,
+        None => policy_retention_years(state.as_ref(), &matter_id).await,
+    };
+
+    let checks = closeout_checks_for_matter(state.as_ref(), &matter_id).await?;
+    let failing: Vec<String> = checks
+        .iter()
+        .filter(|check| !check.passed)
+        .map(|check| format!("{} ({})", check.label, check.detail))
+        .collect();
+    if !failing.is_empty() {
+        return Err((
+            StatusCode::CONFLICT,
+            format!("Matter cannot be closed yet: {}", failing.join("; ")),
+        ));
+    }
+
+    // Whole seconds, so the times in the response match what the store keeps.
+    let closed_at = Utc::now().trunc_subsecs(0);
+    let retain_until = crate::legal::closeout::retain_until(closed_at, retention_years);
+
+    // Render the closing letter before changing anything so a broken
+    // template leaves the matter open.
+    crate::channels::web::server::backfill_matter_templates_from_workspace(
+        state.as_ref(),
+        &matter_id,
+    )
+    .await?;
+    let template = store
+        .get_document_template_by_name(
+            &state.user_id,
+            Some(&matter_id),
+            CLOSING_LETTER_TEMPLATE_NAME,
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let (template_id, body) = match template {
+        Some(template) => (template.id, template.body),
+        None => (Uuid::nil(), CLOSING_LETTER_TEMPLATE_BODY.to_string()),
+    };
+    let client = store
+        .get_client(&state.user_id, matter.client_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Matter is missing an associated client record".to_string(),
+        ))?;
+    let templates = store
+        .list_document_templates(&state.user_id, Some(&matter_id))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let partials = crate::legal::docgen::template_partials(&templates, template_id);
+    let extra = serde_json::json!({
+        "closed_on": closed_at.date_naive().to_string(),
+        "retain_until": retain_until.date_naive().to_string(),
+        "note": note.clone(),
+    });
+    let context = crate::legal::docgen::build_context(&matter, &client, Some(&extra));
+    let letter = crate::legal::docgen::render_template_with_partials(&body, &context, &partials)
+        .map_err(|err| {
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!("Closing letter template failed to render: {err}"),
+            )
+        })?;
+
+    let matter_prefix =
+        crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), &matter_id);
+    let closing_letter_path = crate::channels::web::server::choose_generated_document_destination(
+        workspace.as_ref(),
+        &matter_prefix,
+        CLOSING_LETTER_TEMPLATE_NAME,
+        &closed_at.format("%Y%m%d-%H%M%S").to_string(),
+    )
+    .await?;
+    workspace
+        .write(&closing_letter_path, &letter)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let updated = store
+        .update_matter(
+            &state.user_id,
+            &matter_id,
+            &UpdateMatterParams {
+                client_id: None,
+                status: Some(MatterStatus::Closed),
+                stage: None,
+                practice_area: None,
+                jurisdiction: None,
+                opened_at: None,
+                closed_at: Some(Some(closed_at)),
+                assigned_to: None,
+                custom_fields: None,
+            },
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Matter not found".to_string()))?;
+
+    // The matter is closed from here on; the retention deadline and the
+    // closeout record are best effort.
+    let retention_deadline_id = match store
+        .create_matter_deadline(
+            &state.user_id,
+            &matter_id,
+            &CreateMatterDeadlineParams {
+                title: RETENTION_DEADLINE_TITLE.to_string(),
+                deadline_type: MatterDeadlineType::Internal,
+                due_at: retain_until,
+                completed_at: None,
+                reminder_days: vec![90, 30],
+                rule_ref: None,
+                computed_from: None,
+                task_id: None,
+                explanation: Some(serde_json::json!({
+                    "source": "matter_closeout",
+                    "closed_at": closed_at.to_rfc3339(),
+                    "retention_years": retention_years,
+                })),
+                rule_version: None,
+                is_unsupported: false,
+            },
+        )
+        .await
+    {
+        Ok(deadline) => Some(deadline.id.to_string()),
+        Err(err) => {
+            tracing::warn!(matter_id = %matter_id, "failed to calendar retention deadline: {err}");
+            None
+        }
+    };
+    let closeout_report_path = format!("{matter_prefix}/closeout.md");
+    let report = crate::legal::closeout::closeout_report(
+        &matter_id,
+        closed_at,
+        retain_until,
+        &checks,
+        &closing_letter_path,
+        &principal.user_id,
+        note.as_deref(),
+    );
+    if let Err(err) = workspace.write(&closeout_report_path, &report).await {
+        tracing::warn!(matter_id = %matter_id, "failed to write closeout record: {err}");
+    }
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "matter_closed",
+        &principal.user_id,
+        Some(matter_id.as_str()),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "matter_id": matter_id.clone(),
+            "status": MatterStatus::Closed.as_str(),
+            "source": "closeout",
+            "closing_letter": closing_letter_path.clone(),
+            "retain_until": retain_until.date_naive().to_string(),
+            "retention_years": retention_years,
+        }),
+    )
+    .await;
+
+    Ok(Json(CloseMatterResponse {
+        matter: crate::channels::web::server::db_matter_to_info(state.as_ref(), updated).await,
+        checks,
+        closing_letter_path,
+        closeout_report_path,
+        closed_at: closed_at.to_rfc3339(),
+        retain_until: retain_until.to_rfc3339(),
+        retention_deadline_id,
+    }))
+}
@@ -0,0 +1,163 @@
+//! Document comparison handler.
+//!
+//! `POST /api/matters/{id}/documents/compare` compares two matter documents
+//! (typically the last draft and the text extracted from the executed
+//! contract) with [`crate::legal::compare::compare_documents`] and, with
+//! `write_memo`, files a change memo under the matter's `memos/` folder.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::post,
+};
+use chrono::Utc;
+
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::handlers::helpers::matter::require_matter_access;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{AuditSeverity, MatterMemberRole};
+use crate::legal::compare::{
+    ChangeMemo, compare_documents, default_memo_title, render_change_memo,
+};
+use crate::legal::memo::{file_memo_document, memo_path};
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new().route(
+        "/api/matters/{id}/documents/compare",
+        post(matter_documents_compare_handler),
+    )
+}
+
+pub(crate) async fn matter_documents_compare_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<CompareDocumentsRequest>,
+) -> Result<Json<CompareDocumentsResponse>, (StatusCode, String)> {
+    let role = if req.write_memo {
+        MatterMemberRole::Collaborator
+    } else {
+        MatterMemberRole::Viewer
+    };
+    let matter_id_guard = crate::channels::web::server::sanitize_matter_id_for_route(&id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id_guard,
+        &principal.user_id,
+        role,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let matter_root = crate::channels::web::server::matter_root_for_gateway(state.as_ref());
+    let matter_id = crate::channels::web::server::ensure_existing_matter_for_route(
+        workspace.as_ref(),
+        &matter_root,
+        &id,
+    )
+    .await?;
+    let matter_prefix =
+        crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), &matter_id);
+    let base_path = crate::channels::web::server::matter_document_path(
+        "base_path",
+        &req.base_path,
+        &matter_prefix,
+    )?;
+    let compared_path = crate::channels::web::server::matter_document_path(
+        "compared_path",
+        &req.compared_path,
+        &matter_prefix,
+    )?;
+    if base_path == compared_path {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'base_path' and 'compared_path' must be different documents".to_string(),
+        ));
+    }
+    let title = crate::channels::web::server::parse_optional_matter_field(req.title);
+    crate::channels::web::server::validate_optional_matter_field_length("title", &title)?;
+    let title = title.unwrap_or_else(|| default_memo_title(&base_path, &compared_path));
+
+    let mut contents = Vec::with_capacity(2);
+    for path in [&base_path, &compared_path] {
+        let doc = workspace.read(path).await.map_err(|_| {
+            (
+                StatusCode::NOT_FOUND,
+                format!("Document '{path}' not found"),
+            )
+        })?;
+        contents.push(doc.content);
+    }
+    let comparison = compare_documents(&contents[0], &contents[1]);
+
+    let (mut memo_path_out, mut memo_document_id) = (None, None);
+    if req.write_memo {
+        let store = state.store.as_ref().ok_or((
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Database not available".to_string(),
+        ))?;
+        crate::channels::web::server::ensure_matter_db_row_from_workspace(
+            state.as_ref(),
+            &matter_id,
+        )
+        .await?;
+        let prepared_at = Utc::now();
+        let path = memo_path(&matter_root, &matter_id, prepared_at, &title);
+        let content = render_change_memo(
+            &ChangeMemo {
+                matter_id: &matter_id,
+                title: &title,
+                base_path: &base_path,
+                compared_path: &compared_path,
+                prepared_by: &principal.user_id,
+                prepared_at,
+            },
+            &comparison,
+        );
+        let document = file_memo_document(
+            store.as_ref(),
+            workspace.as_ref(),
+            &state.user_id,
+            &matter_id,
+            &path,
+            &title,
+            &content,
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        crate::channels::web::server::record_legal_audit_event(
+            state.as_ref(),
+            "document_change_memo_filed",
+            &principal.user_id,
+            Some(&matter_id),
+            AuditSeverity::Info,
+            serde_json::json!({
+                "base_path": base_path,
+                "compared_path": compared_path,
+                "memo_path": document.path,
+                "substantive_changes": comparison.substantive,
+                "formatting_changes": comparison.formatting,
+            }),
+        )
+        .await;
+        memo_path_out = Some(document.path);
+        memo_document_id = Some(document.id.to_string());
+    }
+
+    Ok(Json(CompareDocumentsResponse {
+        matter_id,
+        base_path,
+        compared_path,
+        comparison,
+        memo_path: memo_path_out,
+        memo_document_id,
+    }))
+}
@@ -4,8 +4,9 @@ use std::sync::Arc;
 
 use axum::{
     Json, Router,
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
     routing::{get, post},
 };
 
@@ -13,15 +14,27 @@ use crate::channels::web::auth::RequestPrincipal;
 use crate::channels::web::handlers::helpers::matter::require_matter_access;
 use crate::channels::web::state::GatewayState;
 use crate::channels::web::types::{
-    CreatePartyRelationshipRequest, MatterConflictCheckRequest, MatterConflictCheckResponse,
-    MatterConflictClearanceRequest, MatterConflictClearanceResponse,
-    MatterConflictGraphReindexResponse, MatterConflictReportResponse,
-    MatterIntakeConflictCheckRequest, MatterIntakeConflictCheckResponse, MatterPartiesResponse,
-    MatterPartyRelationshipResponse, UpsertMatterPartyRequest,
+    AcceptPartyCandidateRequest, BulkConflictCheckRequest, BulkConflictCheckResponse,
+    BulkConflictCheckResult, CreatePartyRelationshipRequest, MatterConflictCheckRequest,
+    MatterConflictCheckResponse, MatterConflictClearanceRequest, MatterConflictClearanceResponse,
+    MatterConflictGraphReindexResponse, MatterConflictReportResponse, MatterEntitiesQuery,
+    MatterEntitiesResponse, MatterIntakeConflictCheckRequest, MatterIntakeConflictCheckResponse,
+    MatterPartiesResponse, MatterPartyCandidatesResponse, MatterPartyRelationshipResponse,
+    PartyCandidateReviewResponse, PartyCandidatesQuery, UpsertMatterPartyRequest,
 };
-use crate::db::{AuditSeverity, MatterMemberRole, PartyRole, UpsertMatterPartyParams};
+use crate::db::{
+    AuditSeverity, Database, DocumentEntityKind, MatterMemberRole, PartyCandidateRecord,
+    PartyCandidateStatus, PartyRole, ReviewPartyCandidateParams, UpsertMatterPartyParams,
+};
+use crate::events::DomainEvent;
 
 const MAX_CONFLICT_TEXT_PREVIEW_CHARS: usize = 100;
+const MAX_CANDIDATE_CONFLICT_HITS: usize = 10;
+/// Names accepted by one bulk check (e.g. a lateral hire's client list).
+const MAX_BULK_CONFLICT_NAMES: usize = 2000;
+const MAX_BULK_CONFLICT_HITS_PER_NAME: usize = 25;
+const MAX_BULK_CONFLICT_CSV_LEN: usize = 1024 * 1024;
+const MAX_BULK_CONFLICT_LABEL_CHARS: usize = 200;
 
 pub fn routes() -> Router<Arc<GatewayState>> {
     Router::new()
@@ -33,6 +46,10 @@ pub fn routes() -> Router<Arc<GatewayState>> {
             "/api/matters/conflicts/check",
             post(matters_conflicts_check_handler),
         )
+        .route(
+            "/api/legal/conflicts/bulk-check",
+            post(legal_conflicts_bulk_check_handler),
+        )
         .route(
             "/api/matters/conflicts/reindex",
             post(matters_conflicts_reindex_handler),
@@ -45,6 +62,19 @@ pub fn routes() -> Router<Arc<GatewayState>> {
             "/api/matters/{id}/parties/relationships",
             post(matter_parties_relationships_handler),
         )
+        .route(
+            "/api/matters/{id}/party-candidates",
+            get(matter_party_candidates_list_handler),
+        )
+        .route("/api/matters/{id}/entities", get(matter_entities_handler))
+        .route(
+            "/api/matters/{id}/party-candidates/{candidate_id}/accept",
+            post(matter_party_candidate_accept_handler),
+        )
+        .route(
+            "/api/matters/{id}/party-candidates/{candidate_id}/reject",
+            post(matter_party_candidate_reject_handler),
+        )
         .route(
             "/api/matters/{id}/conflicts/report",
             get(matter_conflicts_report_handler),
@@ -165,6 +195,11 @@ pub(crate) async fn matters_conflict_check_handler(
             }),
         )
         .await;
+        state.publish_event(DomainEvent::ConflictHit {
+            matter_id: Some(matter_id.clone()),
+            source: "intake_conflict_check".to_string(),
+            hit_count: hits.len(),
+        });
     }
 
     Ok(Json(MatterIntakeConflictCheckResponse {
@@ -175,6 +210,162 @@ pub(crate) async fn matters_conflict_check_handler(
     }))
 }
 
+/// Screen a long list of names (CSV and/or JSON) against the conflict graph
+/// and return per-name hits, as JSON or as a CSV report download.
+pub(crate) async fn legal_conflicts_bulk_check_handler(
+    State(state): State<Arc<GatewayState>>,
+    Json(req): Json<BulkConflictCheckRequest>,
+) -> Result<Response, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let legal = crate::channels::web::server::legal_config_for_gateway_or_500(state.as_ref())?;
+    if !legal.enabled || !legal.conflict_check_enabled {
+        return Err((
+            StatusCode::CONFLICT,
+            "Conflict check is disabled by legal policy".to_string(),
+        ));
+    }
+    let csv_report = match req.format.as_deref().map(str::trim) {
+        None | Some("") | Some("json") => false,
+        Some("csv") => true,
+        Some(other) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Unknown format '{other}' (expected json or csv)"),
+            ));
+        }
+    };
+    let label = req
+        .label
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty());
+    if label
+        .as_ref()
+        .is_some_and(|label| label.chars().count() > MAX_BULK_CONFLICT_LABEL_CHARS)
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("'label' must be at most {MAX_BULK_CONFLICT_LABEL_CHARS} characters"),
+        ));
+    }
+
+    let mut raw_names = req.names;
+    if let Some(csv) = req.csv.as_deref() {
+        if csv.len() > MAX_BULK_CONFLICT_CSV_LEN {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("'csv' must be at most {MAX_BULK_CONFLICT_CSV_LEN} bytes"),
+            ));
+        }
+        raw_names.extend(
+            crate::legal::bulk_conflicts::parse_names_csv(csv)
+                .map_err(|err| (StatusCode::BAD_REQUEST, err))?,
+        );
+    }
+    let names = crate::legal::bulk_conflicts::dedupe_names(raw_names);
+    if names.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Provide at least one name in 'names' or 'csv'".to_string(),
+        ));
+    }
+    if names.len() > MAX_BULK_CONFLICT_NAMES {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "A bulk check accepts at most {MAX_BULK_CONFLICT_NAMES} distinct names (got {})",
+                names.len()
+            ),
+        ));
+    }
+
+    let rows = crate::legal::bulk_conflicts::screen_names(
+        store.as_ref(),
+        &names,
+        MAX_BULK_CONFLICT_HITS_PER_NAME,
+    )
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let matched_names: Vec<&str> = rows
+        .iter()
+        .filter(|row| !row.hits.is_empty())
+        .map(|row| row.name.as_str())
+        .collect();
+    let hit_count: usize = rows.iter().map(|row| row.hits.len()).sum();
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "bulk_conflict_check",
+        state.user_id.as_str(),
+        None,
+        AuditSeverity::Info,
+        serde_json::json!({
+            "label": label.clone(),
+            "checked_count": names.len(),
+            "matched_count": matched_names.len(),
+            "hit_count": hit_count,
+            "checked_by": state.user_id.clone(),
+        }),
+    )
+    .await;
+    if !matched_names.is_empty() {
+        crate::channels::web::server::record_legal_audit_event(
+            state.as_ref(),
+            "conflict_detected",
+            state.user_id.as_str(),
+            None,
+            AuditSeverity::Warn,
+            serde_json::json!({
+                "source": "bulk_conflict_check",
+                "label": label.clone(),
+                "hit_count": hit_count,
+                "matched_names": matched_names,
+            }),
+        )
+        .await;
+        state.publish_event(DomainEvent::ConflictHit {
+            matter_id: None,
+            source: "bulk_conflict_check".to_string(),
+            hit_count,
+        });
+    }
+
+    if csv_report {
+        let file_name = format!(
+            "conflict-bulk-check-{}.csv",
+            chrono::Utc::now().format("%Y%m%d-%H%M%S")
+        );
+        return Ok((
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{file_name}\""),
+                ),
+            ],
+            crate::legal::bulk_conflicts::report_csv(&rows),
+        )
+            .into_response());
+    }
+    let matched_count = matched_names.len();
+    Ok(Json(BulkConflictCheckResponse {
+        label,
+        checked_count: names.len(),
+        matched_count,
+        results: rows
+            .into_iter()
+            .map(|row| BulkConflictCheckResult {
+                name: row.name,
+                matched: !row.hits.is_empty(),
+                hits: row.hits,
+            })
+            .collect(),
+    })
+    .into_response())
+}
+
 pub(crate) async fn matters_conflicts_check_handler(
     State(state): State<Arc<GatewayState>>,
     Json(req): Json<MatterConflictCheckRequest>,
@@ -290,6 +481,11 @@ pub(crate) async fn matters_conflicts_check_handler(
             }),
         )
         .await;
+        state.publish_event(DomainEvent::ConflictHit {
+            matter_id: effective_matter_id.clone(),
+            source: "manual_text_check".to_string(),
+            hit_count: db_hits.len(),
+        });
     }
 
     Ok(Json(MatterConflictCheckResponse {
@@ -604,6 +800,315 @@ pub(crate) async fn matter_parties_relationships_handler(
     }))
 }
 
+fn parse_party_candidate_status(raw: &str) -> Result<PartyCandidateStatus, (StatusCode, String)> {
+    PartyCandidateStatus::from_db_value(&raw.trim().to_ascii_lowercase()).ok_or((
+        StatusCode::BAD_REQUEST,
+        "status must be one of pending, accepted, or rejected".to_string(),
+    ))
+}
+
+/// Conflict-graph hits for a candidate's name on matters other than its own.
+async fn candidate_conflict_hits(
+    store: &dyn Database,
+    candidate: &PartyCandidateRecord,
+) -> Result<Vec<crate::db::ConflictHit>, (StatusCode, String)> {
+    let hits = store
+        .find_conflict_hits_for_names(
+            std::slice::from_ref(&candidate.name),
+            MAX_CANDIDATE_CONFLICT_HITS,
+        )
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    Ok(hits
+        .into_iter()
+        .filter(|hit| hit.matter_id != candidate.matter_id)
+        .collect())
+}
+
+/// Another reviewer decided the candidate between the load and the update.
+fn candidate_already_reviewed() -> (StatusCode, String) {
+    (
+        StatusCode::CONFLICT,
+        "Party candidate was already reviewed".to_string(),
+    )
+}
+
+async fn load_pending_party_candidate(
+    store: &dyn Database,
+    matter_id: &str,
+    raw_candidate_id: &str,
+) -> Result<PartyCandidateRecord, (StatusCode, String)> {
+    let candidate_id = crate::channels::web::server::parse_uuid(raw_candidate_id, "candidate_id")?;
+    let candidate = store
+        .get_party_candidate(matter_id, candidate_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            "Party candidate not found".to_string(),
+        ))?;
+    if candidate.status != PartyCandidateStatus::Pending {
+        return Err((
+            StatusCode::CONFLICT,
+            format!("Party candidate was already {}", candidate.status.as_str()),
+        ));
+    }
+    Ok(candidate)
+}
+
+pub(crate) async fn matter_party_candidates_list_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Query(query): Query<PartyCandidatesQuery>,
+) -> Result<Json<MatterPartyCandidatesResponse>, (StatusCode, String)> {
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(&id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Viewer,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    crate::channels::web::server::ensure_existing_matter_db(state.as_ref(), &matter_id).await?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let status = query
+        .status
+        .as_deref()
+        .map(parse_party_candidate_status)
+        .transpose()?;
+    let records = store
+        .list_party_candidates(&matter_id, status)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let mut candidates = Vec::with_capacity(records.len());
+    for record in records {
+        // Only pending candidates need a conflict preview; reviewed ones
+        // already live in (or were kept out of) the graph.
+        let hits = if record.status == PartyCandidateStatus::Pending {
+            candidate_conflict_hits(store.as_ref(), &record).await?
+        } else {
+            Vec::new()
+        };
+        candidates.push(crate::channels::web::server::party_candidate_record_to_info(record, hits));
+    }
+    Ok(Json(MatterPartyCandidatesResponse {
+        matter_id,
+        candidates,
+    }))
+}
+
+pub(crate) async fn matter_entities_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Query(query): Query<MatterEntitiesQuery>,
+) -> Result<Json<MatterEntitiesResponse>, (StatusCode, String)> {
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(&id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Viewer,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    crate::channels::web::server::ensure_existing_matter_db(state.as_ref(), &matter_id).await?;
+    ensure_structured_matter_parties(state.as_ref(), &matter_id).await?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let kind = query
+        .kind
+        .as_deref()
+        .map(|raw| {
+            DocumentEntityKind::from_db_value(&raw.trim().to_ascii_lowercase()).ok_or((
+                StatusCode::BAD_REQUEST,
+                "kind must be one of person, organization, date, or amount".to_string(),
+            ))
+        })
+        .transpose()?;
+
+    let entities = store
+        .list_document_entities(&state.user_id, &matter_id, None)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let parties = store
+        .list_matter_parties(&matter_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let relationships = store
+        .list_matter_party_relationships(&matter_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let candidates = store
+        .list_party_candidates(&matter_id, None)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let graph =
+        crate::legal::entities::build_party_graph(&parties, &relationships, &candidates, &entities);
+
+    let listed = entities
+        .into_iter()
+        .filter(|entity| kind.is_none_or(|kind| entity.kind == kind))
+        .collect();
+    Ok(Json(MatterEntitiesResponse {
+        matter_id,
+        entities: crate::channels::web::server::document_entity_records_to_info(listed),
+        nodes: graph.nodes,
+        edges: graph.edges,
+    }))
+}
+
+pub(crate) async fn matter_party_candidate_accept_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path((id, candidate_id)): Path<(String, String)>,
+    Json(req): Json<AcceptPartyCandidateRequest>,
+) -> Result<Json<PartyCandidateReviewResponse>, (StatusCode, String)> {
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(&id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Collaborator,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    crate::channels::web::server::ensure_existing_matter_db(state.as_ref(), &matter_id).await?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let candidate = load_pending_party_candidate(store.as_ref(), &matter_id, &candidate_id).await?;
+    let role = parse_party_role(&req.role)?;
+    let name = crate::channels::web::server::parse_optional_matter_field(req.name)
+        .unwrap_or_else(|| candidate.name.clone());
+    let mut aliases = req
+        .aliases
+        .into_iter()
+        .filter_map(|alias| crate::channels::web::server::parse_optional_matter_field(Some(alias)))
+        .collect::<Vec<_>>();
+    // Keep the name as it appeared in the document searchable when the
+    // reviewer files it under a different canonical name.
+    if crate::db::normalize_party_name(&name) != crate::db::normalize_party_name(&candidate.name) {
+        aliases.push(candidate.name.clone());
+    }
+    let party = store
+        .upsert_matter_party(
+            &matter_id,
+            &UpsertMatterPartyParams {
+                name,
+                role,
+                aliases,
+                notes: None,
+                opened_at: None,
+                closed_at: None,
+            },
+        )
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let reviewed = store
+        .review_party_candidate(
+            &matter_id,
+            candidate.id,
+            &ReviewPartyCandidateParams {
+                status: PartyCandidateStatus::Accepted,
+                reviewed_by: principal.user_id.clone(),
+                party_id: Some(party.party_id),
+            },
+        )
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .ok_or_else(candidate_already_reviewed)?;
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "party_candidate_accepted",
+        principal.user_id.as_str(),
+        Some(matter_id.as_str()),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "candidate_id": reviewed.id.to_string(),
+            "party_id": party.party_id.to_string(),
+            "role": party.role.as_str(),
+            "source_path": reviewed.source_path.clone(),
+        }),
+    )
+    .await;
+    Ok(Json(PartyCandidateReviewResponse {
+        candidate: crate::channels::web::server::party_candidate_record_to_info(
+            reviewed,
+            Vec::new(),
+        ),
+        party: Some(crate::channels::web::server::matter_party_record_to_info(
+            party,
+        )),
+    }))
+}
+
+pub(crate) async fn matter_party_candidate_reject_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path((id, candidate_id)): Path<(String, String)>,
+) -> Result<Json<PartyCandidateReviewResponse>, (StatusCode, String)> {
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(&id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Collaborator,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    crate::channels::web::server::ensure_existing_matter_db(state.as_ref(), &matter_id).await?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let candidate = load_pending_party_candidate(store.as_ref(), &matter_id, &candidate_id).await?;
+    let reviewed = store
+        .review_party_candidate(
+            &matter_id,
+            candidate.id,
+            &ReviewPartyCandidateParams {
+                status: PartyCandidateStatus::Rejected,
+                reviewed_by: principal.user_id.clone(),
+                party_id: None,
+            },
+        )
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .ok_or_else(candidate_already_reviewed)?;
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "party_candidate_rejected",
+        principal.user_id.as_str(),
+        Some(matter_id.as_str()),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "candidate_id": reviewed.id.to_string(),
+            "source_path": reviewed.source_path.clone(),
+        }),
+    )
+    .await;
+    Ok(Json(PartyCandidateReviewResponse {
+        candidate: crate::channels::web::server::party_candidate_record_to_info(
+            reviewed,
+            Vec::new(),
+        ),
+        party: None,
+    }))
+}
+
 pub(crate) async fn matter_conflicts_report_handler(
     State(state): State<Arc<GatewayState>>,
     RequestPrincipal(principal): RequestPrincipal,
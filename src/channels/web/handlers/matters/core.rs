@@ -18,10 +18,13 @@ use crate::channels::web::state::GatewayState;
 use crate::channels::web::types::*;
 use crate::db::{
     AuditSeverity, ClientType, ConflictClearanceRecord, ConflictDecision, ConflictHit,
-    CreateClientParams, CreateMatterDeadlineParams, MatterMemberRole, MatterStatus,
-    OverrideDeadlineParams, UpdateClientParams, UpdateMatterDeadlineParams, UpdateMatterParams,
+    CreateClientParams, CreateMatterDeadlineParams, CreateMatterTaskParams, MatterDeadlineRecord,
+    MatterMemberRole, MatterRecord, MatterStatus, MatterTaskStatus, OverrideDeadlineParams,
+    UpdateClientParams, UpdateMatterDeadlineParams, UpdateMatterParams,
     UpsertMatterMembershipParams, UpsertMatterParams,
 };
+use crate::events::DomainEvent;
+use crate::legal::stages::{StageDefinition, StageWorkflow, TransitionError};
 
 #[derive(Debug, Default, Deserialize)]
 pub(crate) struct ClientsQuery {
@@ -50,6 +53,10 @@ pub fn routes() -> Router<Arc<GatewayState>> {
                 .patch(matter_patch_handler)
                 .delete(matter_delete_handler),
         )
+        .route(
+            "/api/matters/{id}/stage",
+            get(matter_stage_get_handler).post(matter_stage_transition_handler),
+        )
         .route(
             "/api/matters/active",
             get(matters_active_get_handler).post(matters_active_set_handler),
@@ -100,6 +107,30 @@ fn validate_intake_party_name(field_name: &str, value: &str) -> Result<(), (Stat
     Ok(())
 }
 
+/// Validate custom field values against the firm's schemas for
+/// `practice_area` and return them normalized.
+async fn validated_custom_fields(
+    state: &GatewayState,
+    practice_area: Option<&str>,
+    values: &serde_json::Value,
+) -> Result<serde_json::Value, (StatusCode, String)> {
+    if !values.is_object() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'custom_fields' must be a JSON object".to_string(),
+        ));
+    }
+    let schemas =
+        crate::legal::custom_fields::resolve_schemas(state.store.as_ref(), &state.user_id).await;
+    let fields = crate::legal::custom_fields::fields_for_practice_area(&schemas, practice_area);
+    crate::legal::custom_fields::validate_values(&fields, values).map_err(|problems| {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("Custom fields invalid: {}", problems.join("; ")),
+        )
+    })
+}
+
 fn build_checked_parties(client: &str, adversaries: &[String]) -> Vec<String> {
     let mut out = Vec::new();
     if !client.trim().is_empty() {
@@ -369,6 +400,12 @@ pub(crate) async fn matters_create_handler(
     if let Some(value) = opened_date.as_deref() {
         crate::channels::web::server::validate_opened_date(value)?;
     }
+    let custom_fields = validated_custom_fields(
+        state.as_ref(),
+        practice_area.as_deref(),
+        &req.custom_fields.unwrap_or_else(|| serde_json::json!({})),
+    )
+    .await?;
     let team = crate::channels::web::server::parse_matter_list(req.team);
     let adversaries = crate::channels::web::server::parse_matter_list(req.adversaries);
     crate::channels::web::server::validate_intake_party_list("adversaries", &adversaries)?;
@@ -429,74 +466,27 @@ pub(crate) async fn matters_create_handler(
     let matter_yaml = serde_yml::to_string(&metadata)
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    let scaffold = vec![
-        (
-            format!("{matter_prefix}/matter.yaml"),
-            format!(
-                "# Matter metadata schema\n# Required: matter_id, client, confidentiality, retention\n{}",
-                matter_yaml
-            ),
-        ),
-        (
-            format!("{matter_prefix}/README.md"),
-            format!(
-                "# Matter {}\n\nClient: {}\n\nThis workspace stores privileged legal work product.\n\n## Suggested Workflow\n\n1. Intake and conflicts\n2. Facts and chronology\n3. Research and authority synthesis\n4. Drafting and review\n5. Filing and follow-up\n",
-                sanitized, client
-            ),
-        ),
-        (
-            format!("{matter_prefix}/workflows/intake_checklist.md"),
-            "# Intake Checklist\n\n- [ ] Confirm engagement and scope\n- [ ] Confirm client contact and billing details\n- [ ] Run conflict check and document result\n- [ ] Capture key deadlines and court dates\n- [ ] Identify required initial filings or responses\n".to_string(),
-        ),
-        (
-            format!("{matter_prefix}/workflows/review_and_filing_checklist.md"),
-            "# Review and Filing Checklist\n\n- [ ] Separate facts from analysis in final draft\n- [ ] Verify citation format coverage for factual/legal assertions\n- [ ] Confirm privilege/confidentiality review complete\n- [ ] Final QA pass and attorney approval recorded\n- [ ] Filing/service steps completed and logged\n".to_string(),
-        ),
-        (
-            format!("{matter_prefix}/deadlines/calendar.md"),
-            "# Deadlines and Hearings\n\n| Date | Deadline / Event | Owner | Status | Source |\n|---|---|---|---|---|\n".to_string(),
-        ),
-        (
-            format!("{matter_prefix}/facts/key_facts.md"),
-            "# Key Facts Log\n\n| Fact | Source | Confidence | Notes |\n|---|---|---|---|\n".to_string(),
-        ),
-        (
-            format!("{matter_prefix}/research/authority_table.md"),
-            "# Authority Table\n\n| Authority | Holding / Principle | Relevance | Risk / Limit | Citation |\n|---|---|---|---|---|\n".to_string(),
-        ),
-        (
-            format!("{matter_prefix}/discovery/request_tracker.md"),
-            "# Discovery Request Tracker\n\n| Request / Topic | Served / Received | Response Due | Status | Notes |\n|---|---|---|---|---|\n".to_string(),
-        ),
-        (
-            format!("{matter_prefix}/communications/contact_log.md"),
-            "# Communications Log\n\n| Date | With | Channel | Summary | Follow-up |\n|---|---|---|---|---|\n".to_string(),
-        ),
-        (
-            format!("{matter_prefix}/templates/research_memo.md"),
-            "# Research Memo Template\n\n## Question Presented\n\n## Brief Answer\n\n## Facts (Cited)\n\n## Analysis\n\n## Authorities\n\n## Open Questions\n".to_string(),
-        ),
-        (
-            format!("{matter_prefix}/templates/chronology.md"),
-            "# Chronology\n\n| Date | Event | Source |\n|---|---|---|\n".to_string(),
-        ),
-        (
-            format!("{matter_prefix}/templates/legal_memo.md"),
-            "# Legal Memo Template\n\n## Issue\n\n## Brief Answer\n\n## Facts (Cited)\n\n## Analysis\n\n## Conclusion\n\n## Risk / Uncertainty\n".to_string(),
-        ),
-        (
-            format!("{matter_prefix}/templates/contract_issues.md"),
-            "# Contract Issue List\n\n| Clause / Topic | Risk | Recommendation | Source |\n|---|---|---|---|\n".to_string(),
-        ),
-        (
-            format!("{matter_prefix}/templates/discovery_plan.md"),
-            "# Discovery Plan\n\n## Custodians\n\n## Data Sources\n\n## Requests\n\n## Objections / Risks\n\n## Source Traceability\n".to_string(),
-        ),
-        (
-            format!("{matter_prefix}/templates/research_synthesis.md"),
-            "# Research Synthesis\n\n## Question Presented\n\n## Authorities Reviewed\n\n## Facts (Cited)\n\n## Analysis\n\n## Risk / Uncertainty\n".to_string(),
-        ),
-    ];
+    let practice_scaffold =
+        crate::legal::scaffold::scaffold_for_practice_area(practice_area.as_deref());
+    let mut scaffold = crate::legal::scaffold::matter_scaffold_files(
+        &crate::legal::scaffold::ScaffoldContext {
+            matter_id: &sanitized,
+            client: &client,
+            matter_yaml: &matter_yaml,
+        },
+        practice_scaffold,
+    );
+    // Firm templates are rendered before any DB writes so a broken template
+    // fails the request cleanly instead of leaving a half-created matter.
+    let firm_templates = crate::legal::scaffold::load_workspace_templates(workspace, &matter_root)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    crate::legal::scaffold::apply_templates(
+        &mut scaffold,
+        &firm_templates,
+        &crate::legal::scaffold::template_context(&metadata, practice_scaffold),
+    )
+    .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))?;
 
     let opened_at_ts =
         crate::channels::web::server::parse_optional_datetime("opened_date", opened_date.clone())?;
@@ -527,7 +517,7 @@ pub(crate) async fn matters_create_handler(
                 opened_at: opened_at_ts,
                 closed_at: None,
                 assigned_to: team.clone(),
-                custom_fields: serde_json::json!({}),
+                custom_fields,
             },
         )
         .await
@@ -543,7 +533,7 @@ pub(crate) async fn matters_create_handler(
 
     for (path, content) in scaffold {
         workspace
-            .write(&path, &content)
+            .write(&format!("{matter_prefix}/{path}"), &content)
             .await
             .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     }
@@ -568,9 +558,25 @@ pub(crate) async fn matters_create_handler(
             "matter_id": sanitized.clone(),
             "client_id": db_client.id.to_string(),
             "status": MatterStatus::Active.as_str(),
+            "scaffold": practice_scaffold.map(|scaffold| scaffold.id),
         }),
     )
     .await;
+    state.publish_event(DomainEvent::MatterCreated {
+        matter_id: sanitized.clone(),
+        client_id: db_client.id.to_string(),
+        status: MatterStatus::Active.as_str().to_string(),
+        practice_area: practice_area.clone(),
+        jurisdiction: jurisdiction.clone(),
+        opened_date: opened_date.clone(),
+    });
+    crate::channels::web::handlers::watchlist::screen_parties_against_watchlist(
+        state.as_ref(),
+        Some(sanitized.as_str()),
+        "matter_created",
+        &checked_parties,
+    )
+    .await;
 
     Ok((
         StatusCode::CREATED,
@@ -706,6 +712,13 @@ pub(crate) async fn clients_create_handler(
         )
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    crate::channels::web::handlers::watchlist::screen_parties_against_watchlist(
+        state.as_ref(),
+        None,
+        "client_created",
+        std::slice::from_ref(&client.name),
+    )
+    .await;
     Ok((
         StatusCode::CREATED,
         Json(crate::channels::web::server::client_record_to_info(client)),
@@ -871,31 +884,54 @@ pub(crate) async fn matter_patch_handler(
     let assigned_to = req
         .assigned_to
         .map(crate::channels::web::server::parse_matter_list);
-    let custom_fields = if let Some(value) = req.custom_fields {
-        if !value.is_object() {
+    let practice_area = req.practice_area.map(|value| {
+        value.and_then(|inner| {
+            crate::channels::web::server::parse_optional_matter_field(Some(inner))
+        })
+    });
+    // Custom fields are checked against the schemas for the practice area the
+    // matter will have after the update, so changing either revalidates.
+    let custom_fields = if req.custom_fields.is_some() || practice_area.is_some() {
+        let current = store
+            .get_matter_db(&state.user_id, &matter_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or((StatusCode::NOT_FOUND, "Matter not found".to_string()))?;
+        let effective_area = match &practice_area {
+            Some(area) => area.clone(),
+            None => current.practice_area.clone(),
+        };
+        let values = req.custom_fields.unwrap_or(current.custom_fields);
+        Some(validated_custom_fields(state.as_ref(), effective_area.as_deref(), &values).await?)
+    } else {
+        None
+    };
+
+    // Stage changes go through the workflow endpoint so transition rules and
+    // prerequisites apply; echoing the current stage back is still accepted.
+    if let Some(stage) = req.stage {
+        let requested = stage.and_then(|inner| {
+            crate::channels::web::server::parse_optional_matter_field(Some(inner))
+        });
+        let current = store
+            .get_matter_db(&state.user_id, &matter_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or((StatusCode::NOT_FOUND, "Matter not found".to_string()))?
+            .stage;
+        if requested != current {
             return Err((
                 StatusCode::BAD_REQUEST,
-                "'custom_fields' must be a JSON object".to_string(),
+                format!("Use POST /api/matters/{matter_id}/stage to change the matter stage"),
             ));
         }
-        Some(value)
-    } else {
-        None
-    };
+    }
 
     let input = UpdateMatterParams {
         client_id,
         status,
-        stage: req.stage.map(|value| {
-            value.and_then(|inner| {
-                crate::channels::web::server::parse_optional_matter_field(Some(inner))
-            })
-        }),
-        practice_area: req.practice_area.map(|value| {
-            value.and_then(|inner| {
-                crate::channels::web::server::parse_optional_matter_field(Some(inner))
-            })
-        }),
+        stage: None,
+        practice_area,
         jurisdiction: req.jurisdiction.map(|value| {
             value.and_then(|inner| {
                 crate::channels::web::server::parse_optional_matter_field(Some(inner))
@@ -973,6 +1009,263 @@ pub(crate) async fn matter_patch_handler(
     ))
 }
 
+async fn matter_stage_workflow(
+    state: &GatewayState,
+    matter: &MatterRecord,
+) -> Result<StageWorkflow, (StatusCode, String)> {
+    let workflows = crate::legal::stages::resolve_workflows(state.store.as_ref(), &state.user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    crate::legal::stages::workflow_for_practice_area(&workflows, matter.practice_area.as_deref())
+        .cloned()
+        .ok_or((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "No matter stage workflows are configured".to_string(),
+        ))
+}
+
+/// Entry prerequisites of `stage` the matter's workspace does not meet yet.
+async fn missing_stage_prerequisites(
+    state: &GatewayState,
+    matter_id: &str,
+    stage: &StageDefinition,
+) -> Vec<String> {
+    let Some(workspace) = state.workspace.as_ref() else {
+        return stage
+            .required_artifacts
+            .iter()
+            .chain(&stage.complete_checklists)
+            .map(|path| format!("{path} cannot be checked (workspace unavailable)"))
+            .collect();
+    };
+    let prefix = crate::channels::web::server::matter_prefix_for_gateway(state, matter_id);
+    let mut missing = Vec::new();
+    for path in &stage.required_artifacts {
+        match workspace.read(&format!("{prefix}/{path}")).await {
+            Ok(doc) if !doc.content.trim().is_empty() => {}
+            _ => missing.push(format!("{path} is missing")),
+        }
+    }
+    for path in &stage.complete_checklists {
+        match workspace.read(&format!("{prefix}/{path}")).await {
+            Ok(doc) => {
+                let (completed, total) =
+                    crate::channels::web::server::checklist_completion_from_markdown(&doc.content);
+                if completed < total {
+                    missing.push(format!(
+                        "{path} has {} of {total} items open",
+                        total - completed
+                    ));
+                }
+            }
+            Err(_) => missing.push(format!("{path} is missing")),
+        }
+    }
+    missing
+}
+
+pub(crate) async fn matter_stage_get_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<MatterStageResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(&id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Viewer,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    let matter = store
+        .get_matter_db(&state.user_id, &matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Matter not found".to_string()))?;
+    let workflow = matter_stage_workflow(state.as_ref(), &matter).await?;
+    let mut next = Vec::new();
+    for stage in workflow.allowed_next(matter.stage.as_deref()) {
+        next.push(MatterStageOption {
+            id: stage.id.clone(),
+            label: stage.label.clone(),
+            missing: missing_stage_prerequisites(state.as_ref(), &matter_id, stage).await,
+        });
+    }
+    Ok(Json(MatterStageResponse {
+        stage_label: matter
+            .stage
+            .as_deref()
+            .and_then(|id| workflow.stage(id))
+            .map(|stage| stage.label.clone()),
+        stage: matter.stage,
+        workflow_id: workflow.id,
+        workflow_label: workflow.label,
+        next,
+    }))
+}
+
+pub(crate) async fn matter_stage_transition_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<MatterStageTransitionRequest>,
+) -> Result<Json<MatterStageTransitionResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(&id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Collaborator,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    let target = crate::channels::web::server::parse_required_matter_field("stage", &req.stage)?;
+    let note = crate::channels::web::server::parse_optional_matter_field(req.note);
+    crate::channels::web::server::validate_optional_matter_field_length("note", &note)?;
+    let matter = store
+        .get_matter_db(&state.user_id, &matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Matter not found".to_string()))?;
+    let from = matter.stage.clone();
+    if from.as_deref() == Some(target.as_str()) {
+        return Err((
+            StatusCode::CONFLICT,
+            format!("Matter is already in stage '{target}'"),
+        ));
+    }
+
+    let workflow = matter_stage_workflow(state.as_ref(), &matter).await?;
+    let stage = crate::legal::stages::check_transition(&workflow, from.as_deref(), &target)
+        .map_err(|err| match err {
+            TransitionError::UnknownStage { .. } => (StatusCode::BAD_REQUEST, err.to_string()),
+            TransitionError::NotAllowed { .. } => (StatusCode::CONFLICT, err.to_string()),
+        })?;
+    let missing = missing_stage_prerequisites(state.as_ref(), &matter_id, stage).await;
+    if !missing.is_empty() {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!(
+                "Cannot enter stage '{}' yet: {}",
+                stage.id,
+                missing.join("; ")
+            ),
+        ));
+    }
+
+    let updated = store
+        .update_matter(
+            &state.user_id,
+            &matter_id,
+            &UpdateMatterParams {
+                client_id: None,
+                status: None,
+                stage: Some(Some(stage.id.clone())),
+                practice_area: None,
+                jurisdiction: None,
+                opened_at: None,
+                closed_at: None,
+                assigned_to: None,
+                custom_fields: None,
+            },
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Matter not found".to_string()))?;
+
+    // Stage tasks are best effort once the stage has moved; a task that is
+    // still open from an earlier visit to the stage is not duplicated.
+    let open_titles: HashSet<String> = match store
+        .list_matter_tasks(&state.user_id, &matter_id)
+        .await
+    {
+        Ok(tasks) => tasks
+            .into_iter()
+            .filter(|task| {
+                !matches!(
+                    task.status,
+                    MatterTaskStatus::Done | MatterTaskStatus::Cancelled
+                )
+            })
+            .map(|task| task.title)
+            .collect(),
+        Err(err) => {
+            tracing::warn!(matter_id = %matter_id, "failed to list tasks for stage change: {err}");
+            HashSet::new()
+        }
+    };
+    let mut created_tasks = Vec::new();
+    for title in stage
+        .tasks
+        .iter()
+        .filter(|title| !open_titles.contains(*title))
+    {
+        match store
+            .create_matter_task(
+                &state.user_id,
+                &matter_id,
+                &CreateMatterTaskParams {
+                    title: title.clone(),
+                    description: Some(format!(
+                        "Created when the matter entered the {} stage.",
+                        stage.label
+                    )),
+                    status: MatterTaskStatus::Todo,
+                    assignee: None,
+                    due_at: None,
+                    blocked_by: Vec::new(),
+                    parent_task_id: None,
+                    checklist: Vec::new(),
+                },
+            )
+            .await
+        {
+            Ok(task) => created_tasks.push(
+                crate::channels::web::server::matter_task_record_to_info(task),
+            ),
+            Err(err) => {
+                tracing::warn!(matter_id = %matter_id, "failed to create stage task '{title}': {err}")
+            }
+        }
+    }
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "matter_stage_changed",
+        &principal.user_id,
+        Some(matter_id.as_str()),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "matter_id": matter_id.clone(),
+            "workflow": workflow.id.clone(),
+            "from": from.clone(),
+            "to": stage.id.clone(),
+            "note": note,
+            "created_tasks": created_tasks.len(),
+        }),
+    )
+    .await;
+
+    Ok(Json(MatterStageTransitionResponse {
+        matter: crate::channels::web::server::db_matter_to_info(state.as_ref(), updated).await,
+        workflow_id: workflow.id.clone(),
+        from,
+        to: stage.id.clone(),
+        created_tasks,
+    }))
+}
+
 pub(crate) async fn matter_delete_handler(
     State(state): State<Arc<GatewayState>>,
     RequestPrincipal(principal): RequestPrincipal,
@@ -1245,6 +1538,7 @@ fn court_rule_to_info(rule: &crate::legal::calendar::CourtRule) -> CourtRuleInfo
         id: rule.id.clone(),
         citation: rule.citation.clone(),
         deadline_type: rule.deadline_type.as_str().to_string(),
+        offset_months: rule.offset_months,
         offset_days: rule.offset_days,
         court_days: rule.court_days,
         version: rule.version.clone(),
@@ -1368,6 +1662,18 @@ async fn cascade_recompute_deadline_dependents(
     Ok(())
 }
 
+pub(crate) fn deadline_added_event(record: &MatterDeadlineRecord, actor: &str) -> DomainEvent {
+    DomainEvent::DeadlineAdded {
+        matter_id: record.matter_id.clone(),
+        deadline_id: record.id,
+        title: record.title.clone(),
+        deadline_type: record.deadline_type.as_str().to_string(),
+        due_at: record.due_at,
+        rule_ref: record.rule_ref.clone(),
+        actor: actor.to_string(),
+    }
+}
+
 pub(crate) async fn matter_deadlines_create_handler(
     State(state): State<Arc<GatewayState>>,
     RequestPrincipal(principal): RequestPrincipal,
@@ -1481,6 +1787,7 @@ pub(crate) async fn matter_deadlines_create_handler(
         &created,
     )
     .await?;
+    state.publish_event(deadline_added_event(&created, &principal.user_id));
 
     Ok((
         StatusCode::CREATED,
@@ -1756,6 +2063,7 @@ pub(crate) async fn matter_deadlines_compute_handler(
             &record,
         )
         .await?;
+        state.publish_event(deadline_added_event(&record, &principal.user_id));
         Some(crate::channels::web::server::deadline_record_to_info(
             record,
         ))
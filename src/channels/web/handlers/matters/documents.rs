@@ -16,15 +16,17 @@ use crate::channels::web::server::MatterDocumentsQuery;
 use crate::channels::web::state::GatewayState;
 use crate::channels::web::types::*;
 use crate::db::{
-    CreateDocumentVersionParams, DocumentReadinessState, MatterDocumentCategory, MatterMemberRole,
-    UpsertMatterDocumentParams,
+    CreateDocumentVersionParams, DocumentReadinessState, ExhibitStatus, MatterDocumentCategory,
+    MatterMemberRole, UpsertMatterDocumentParams,
 };
+use crate::events::DomainEvent;
 use crate::legal::citations::CitationVerificationProvider;
 
 pub fn routes() -> Router<Arc<GatewayState>> {
     Router::new()
         .route("/api/matters/{id}/documents", get(matter_documents_handler))
         .route("/api/matters/{id}/dashboard", get(matter_dashboard_handler))
+        .route("/api/matters/{id}/storage", get(matter_storage_handler))
         .route("/api/matters/{id}/templates", get(matter_templates_handler))
         .route(
             "/api/matters/{id}/templates/apply",
@@ -35,6 +37,11 @@ pub fn routes() -> Router<Arc<GatewayState>> {
             post(matter_retrieval_export_handler),
         )
         .route("/api/documents/generate", post(documents_generate_handler))
+        .route("/api/documents/caption", post(document_caption_handler))
+        .route(
+            "/api/templates/{id}/variables",
+            get(template_variables_handler).put(template_variables_update_handler),
+        )
         .route(
             "/api/matters/{id}/citations/verify",
             post(matter_citations_verify_handler),
@@ -44,10 +51,30 @@ pub fn routes() -> Router<Arc<GatewayState>> {
             get(document_citations_handler),
         )
         .route("/api/documents/{id}/ready", post(document_ready_handler))
+        .route(
+            "/api/matters/{id}/translations",
+            get(matter_translations_handler),
+        )
+        .route(
+            "/api/matters/{id}/translations/{translation_id}",
+            get(matter_translation_detail_handler),
+        )
+        .route(
+            "/api/matters/{id}/translations/{translation_id}/request-certification",
+            post(matter_translation_request_certification_handler),
+        )
+        .route(
+            "/api/matters/{id}/translations/{translation_id}/certify",
+            post(matter_translation_certify_handler),
+        )
         .route(
             "/api/matters/{id}/filing-package",
             post(matter_filing_package_handler),
         )
+        .route(
+            "/api/matters/{id}/filing-package/validation",
+            get(matter_filing_validation_handler),
+        )
 }
 
 pub(crate) async fn matter_documents_handler(
@@ -151,6 +178,68 @@ pub(crate) async fn matter_documents_handler(
     }))
 }
 
+pub(crate) async fn matter_storage_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<MatterStorageResponse>, (StatusCode, String)> {
+    let matter_id_guard = crate::channels::web::server::sanitize_matter_id_for_route(&id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id_guard,
+        &principal.user_id,
+        MatterMemberRole::Viewer,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let legal = crate::channels::web::server::legal_config_for_gateway_or_500(state.as_ref())?;
+    let matter_root = crate::channels::web::server::matter_root_for_gateway(state.as_ref());
+    let matter_id = crate::channels::web::server::ensure_existing_matter_for_route(
+        workspace.as_ref(),
+        &matter_root,
+        &id,
+    )
+    .await?;
+
+    let report = crate::legal::storage::matter_storage_report(
+        workspace.as_ref(),
+        store.as_ref(),
+        &legal,
+        &matter_id,
+    )
+    .await
+    .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?;
+    let warning = report.quota.warning_message(&matter_id);
+    Ok(Json(MatterStorageResponse {
+        matter_id: report.matter_id,
+        document_count: report.usage.document_count,
+        document_bytes: report.usage.document_bytes,
+        chunk_count: report.usage.chunk_count,
+        chunk_bytes: report.usage.chunk_bytes,
+        embedded_chunk_count: report.usage.embedded_chunk_count,
+        binary_count: report.usage.binary_count,
+        binary_bytes: report.usage.binary_bytes,
+        version_count: report.version_count,
+        total_bytes: report.usage.total_bytes(),
+        quota: MatterStorageQuotaInfo {
+            status: report.quota.status,
+            soft_quota_bytes: report.quota.soft_quota_bytes,
+            warn_at_percent: report.quota.warn_at_percent,
+            percent_used: report.quota.percent_used,
+            warning,
+        },
+    }))
+}
+
 pub(crate) async fn matter_dashboard_handler(
     State(state): State<Arc<GatewayState>>,
     RequestPrincipal(principal): RequestPrincipal,
@@ -261,6 +350,17 @@ pub(crate) async fn matter_dashboard_handler(
     }))
 }
 
+/// Pleadings and filings that go into a filing package. Earlier package
+/// exports are classified as filings by path but are not themselves filed.
+fn is_package_filing(matter_prefix: &str, document: &crate::db::MatterDocumentRecord) -> bool {
+    matches!(
+        document.category,
+        MatterDocumentCategory::Pleading | MatterDocumentCategory::Filing
+    ) && !document
+        .path
+        .starts_with(&format!("{matter_prefix}/exports/"))
+}
+
 async fn ensure_ready_filing_documents(
     state: &GatewayState,
     matter_id: &str,
@@ -275,11 +375,10 @@ async fn ensure_ready_filing_documents(
         .list_matter_documents_db(&state.user_id, matter_id)
         .await
         .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let matter_prefix = crate::channels::web::server::matter_prefix_for_gateway(state, matter_id);
     if let Some(blocking) = documents.into_iter().find(|document| {
-        matches!(
-            document.category,
-            MatterDocumentCategory::Pleading | MatterDocumentCategory::Filing
-        ) && document.readiness_state != DocumentReadinessState::ReadyToFile
+        is_package_filing(&matter_prefix, document)
+            && document.readiness_state != DocumentReadinessState::ReadyToFile
     }) {
         return Err((
             StatusCode::CONFLICT,
@@ -431,6 +530,7 @@ pub(crate) async fn matter_template_apply_handler(
         .write(&destination, &template_body)
         .await
         .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let mut document_id = None;
     if let Some(store) = state.store.as_ref() {
         let linked = store
             .upsert_matter_document(
@@ -469,7 +569,15 @@ pub(crate) async fn matter_template_apply_handler(
             let _ = workspace.delete(&destination).await;
             return Err((StatusCode::INTERNAL_SERVER_ERROR, err.to_string()));
         }
+        document_id = Some(linked.id);
     }
+    state.publish_event(DomainEvent::DocumentGenerated {
+        matter_id: matter_id.clone(),
+        document_id,
+        path: destination.clone(),
+        template: template_name.clone(),
+        actor: principal.user_id.clone(),
+    });
 
     Ok((
         StatusCode::CREATED,
@@ -562,6 +670,127 @@ pub(crate) async fn matter_retrieval_export_handler(
     ))
 }
 
+/// Load a template and check the principal's access to it. Matter templates
+/// follow matter roles; shared templates are readable by everyone and
+/// editable by the workspace owner.
+pub(crate) async fn template_for_principal(
+    state: &GatewayState,
+    principal_user_id: &str,
+    raw_id: &str,
+    role: MatterMemberRole,
+) -> Result<crate::db::DocumentTemplateRecord, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let template_id = crate::channels::web::server::parse_uuid(raw_id.trim(), "template_id")?;
+    let template = store
+        .get_document_template(&state.user_id, template_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Template not found".to_string()))?;
+    match template.matter_id.as_deref() {
+        Some(matter_id) => {
+            require_matter_access(
+                &state.store,
+                &state.user_id,
+                matter_id,
+                principal_user_id,
+                role,
+            )
+            .await
+            .map_err(|sc| (sc, "Insufficient permissions".to_string()))?;
+        }
+        None if role != MatterMemberRole::Viewer && principal_user_id != state.user_id => {
+            return Err((
+                StatusCode::FORBIDDEN,
+                "Insufficient permissions".to_string(),
+            ));
+        }
+        None => {}
+    }
+    Ok(template)
+}
+
+fn template_variables_response(
+    template: crate::db::DocumentTemplateRecord,
+) -> Result<TemplateVariablesResponse, (StatusCode, String)> {
+    let variables =
+        crate::legal::docgen::parse_variable_schema(&template.variables_json).map_err(|err| {
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!("Template variable schema is invalid: {err}"),
+            )
+        })?;
+    Ok(TemplateVariablesResponse {
+        template_id: template.id.to_string(),
+        matter_id: template.matter_id,
+        name: template.name,
+        variables,
+    })
+}
+
+/// `GET /api/templates/{id}/variables` — the template's typed variable
+/// schema, for building a fill-in form before generating.
+pub(crate) async fn template_variables_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<TemplateVariablesResponse>, (StatusCode, String)> {
+    let template = template_for_principal(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Viewer,
+    )
+    .await?;
+    Ok(Json(template_variables_response(template)?))
+}
+
+/// `PUT /api/templates/{id}/variables` — replace the variable schema.
+pub(crate) async fn template_variables_update_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateTemplateVariablesRequest>,
+) -> Result<Json<TemplateVariablesResponse>, (StatusCode, String)> {
+    let template = template_for_principal(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let variables = crate::legal::docgen::parse_variable_schema(&req.variables)
+        .map_err(|err| (StatusCode::BAD_REQUEST, err))?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let variables_json = serde_json::to_value(&variables)
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let updated = store
+        .update_document_template(
+            &state.user_id,
+            template.id,
+            &crate::db::UpdateDocumentTemplateParams {
+                name: None,
+                body: None,
+                variables_json: Some(variables_json),
+            },
+        )
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Template not found".to_string()))?;
+    if updated.matter_id.is_none() {
+        store
+            .record_document_template_version(&state.user_id, updated.id, &principal.user_id)
+            .await
+            .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    }
+    Ok(Json(template_variables_response(updated)?))
+}
+
 pub(crate) async fn documents_generate_handler(
     State(state): State<Arc<GatewayState>>,
     RequestPrincipal(principal): RequestPrincipal,
@@ -633,14 +862,75 @@ pub(crate) async fn documents_generate_handler(
             "Matter is missing an associated client record".to_string(),
         ))?;
 
+    let locale = match crate::channels::web::server::parse_optional_matter_field(req.locale) {
+        Some(raw) => crate::legal::locale::Locale::parse(&raw).ok_or((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Unknown locale '{raw}' (expected one of: {})",
+                crate::legal::locale::Locale::ALL
+                    .iter()
+                    .map(|locale| locale.code())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ))?,
+        None => {
+            crate::legal::client_comms::preferred_locale(store.as_ref(), &state.user_id, client.id)
+                .await
+                .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        }
+    };
+    let templates = store
+        .list_document_templates(&state.user_id, Some(&matter_id))
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let template = crate::legal::docgen::template_variant(&templates, &template, locale)
+        .cloned()
+        .unwrap_or(template);
+
+    let schema =
+        crate::legal::docgen::parse_variable_schema(&template.variables_json).map_err(|err| {
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!("Template variable schema is invalid: {err}"),
+            )
+        })?;
     let extra = if req.extra.is_object() {
         req.extra
     } else {
         serde_json::json!({})
     };
-    let context = crate::legal::docgen::build_context(&matter, &client, Some(&extra));
-    let rendered = crate::legal::docgen::render_template(&template.body, &context)
-        .map_err(|err| (StatusCode::BAD_REQUEST, err))?;
+    let extra = crate::legal::docgen::validate_variables(&schema, &extra).map_err(|problems| {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("Template variables invalid: {}", problems.join("; ")),
+        )
+    })?;
+    let mut partials = crate::legal::docgen::template_partials(&templates, template.id);
+    crate::legal::docgen::localize_partials(&mut partials, locale);
+    let parties = store
+        .list_matter_parties(&matter_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    let custom_field_schemas =
+        crate::legal::custom_fields::resolve_schemas(state.store.as_ref(), &state.user_id).await;
+    let custom_fields = crate::legal::custom_fields::fields_for_practice_area(
+        &custom_field_schemas,
+        matter.practice_area.as_deref(),
+    );
+    let mut context = crate::legal::docgen::build_context(&matter, &client, Some(&extra));
+    crate::legal::docgen::insert_caption_context(
+        &mut context,
+        &crate::legal::caption::matter_caption(&matter, &client, &parties, None),
+    );
+    crate::legal::docgen::insert_custom_field_context(
+        &mut context,
+        crate::legal::custom_fields::template_values(&custom_fields, &matter.custom_fields),
+    );
+    crate::legal::docgen::insert_locale_context(&mut context, locale);
+    let rendered =
+        crate::legal::docgen::render_template_with_partials(&template.body, &context, &partials)
+            .map_err(|err| (StatusCode::BAD_REQUEST, err))?;
 
     let category =
         crate::channels::web::server::parse_matter_document_category(req.category.as_deref())?;
@@ -712,6 +1002,27 @@ pub(crate) async fn documents_generate_handler(
             return Err((StatusCode::INTERNAL_SERVER_ERROR, err.to_string()));
         }
     };
+    if let Err(err) = store
+        .record_document_template_use(
+            &state.user_id,
+            &crate::db::RecordDocumentTemplateUseParams {
+                template_id: template.id,
+                matter_id: matter_id.clone(),
+                document_path: linked.path.clone(),
+                used_by: principal.user_id.clone(),
+            },
+        )
+        .await
+    {
+        tracing::warn!(template_id = %template.id, "Failed to record template use: {err}");
+    }
+    state.publish_event(DomainEvent::DocumentGenerated {
+        matter_id: matter_id.clone(),
+        document_id: Some(linked.id),
+        path: linked.path.clone(),
+        template: template.name.clone(),
+        actor: principal.user_id.clone(),
+    });
 
     Ok((
         StatusCode::CREATED,
@@ -724,10 +1035,128 @@ pub(crate) async fn documents_generate_handler(
             readiness_state: linked.readiness_state.as_str().to_string(),
             version_number: version.version_number,
             label: version.label,
+            locale: locale.code().to_string(),
+            template_name: template.name,
         }),
     ))
 }
 
+/// `POST /api/documents/caption` — court caption, signature block, and
+/// certificate of service for a matter, formatted for its jurisdiction.
+/// Request fields override what the matter records.
+pub(crate) async fn document_caption_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Json(req): Json<DocumentCaptionRequest>,
+) -> Result<Json<DocumentCaptionResponse>, (StatusCode, String)> {
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(&req.matter_id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Viewer,
+    )
+    .await
+    .map_err(|sc| (sc, "Insufficient permissions".to_string()))?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let title = crate::channels::web::server::parse_required_matter_field("title", &req.title)?;
+    let side = match req.client_position.as_deref() {
+        Some(raw) => Some(crate::legal::caption::ClientSide::parse(raw).ok_or((
+            StatusCode::BAD_REQUEST,
+            "'client_position' must be 'plaintiff' or 'defendant'".to_string(),
+        ))?),
+        None => None,
+    };
+    let date = match req.date.as_deref() {
+        Some(raw) => crate::channels::web::server::parse_datetime_value("date", raw)?.date_naive(),
+        None => Utc::now().date_naive(),
+    };
+    if !req.served.is_empty() && req.attorney.is_none() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'attorney' is required to sign a certificate of service".to_string(),
+        ));
+    }
+
+    crate::channels::web::server::ensure_matter_db_row_from_workspace(state.as_ref(), &matter_id)
+        .await?;
+    let matter = store
+        .get_matter_db(&state.user_id, &matter_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Matter not found".to_string()))?;
+    let client = store
+        .get_client(&state.user_id, matter.client_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .ok_or((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Matter is missing an associated client record".to_string(),
+        ))?;
+    let parties = store
+        .list_matter_parties(&matter_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+
+    let mut caption = crate::legal::caption::matter_caption(&matter, &client, &parties, side);
+    if req.jurisdiction.is_some() || req.court.is_some() {
+        let court = crate::channels::web::server::parse_optional_matter_field(req.court)
+            .or_else(|| crate::legal::caption::matter_court(&matter));
+        let jurisdiction =
+            crate::channels::web::server::parse_optional_matter_field(req.jurisdiction)
+                .or(matter.jurisdiction.clone());
+        caption.profile =
+            crate::legal::caption::caption_profile(jurisdiction.as_deref(), court.as_deref());
+    }
+    if let Some(case_number) =
+        crate::channels::web::server::parse_optional_matter_field(req.case_number)
+    {
+        caption.parties.case_number = Some(case_number);
+    }
+    if let Some(plaintiffs) = req.plaintiffs {
+        caption.parties.plaintiffs = crate::channels::web::server::parse_matter_list(plaintiffs);
+    }
+    if let Some(defendants) = req.defendants {
+        caption.parties.defendants = crate::channels::web::server::parse_matter_list(defendants);
+    }
+
+    let signature_block = req.attorney.as_ref().map(|attorney| {
+        crate::legal::caption::render_signature_block(
+            &caption.profile,
+            attorney,
+            &caption.parties,
+            date,
+        )
+    });
+    let certificate_of_service = req
+        .attorney
+        .as_ref()
+        .filter(|_| !req.served.is_empty())
+        .map(|attorney| {
+            crate::legal::caption::render_certificate_of_service(
+                &caption.profile,
+                &title,
+                &req.served,
+                date,
+                &attorney.name,
+            )
+        });
+    Ok(Json(DocumentCaptionResponse {
+        style: caption.profile.style.as_str(),
+        caption: crate::legal::caption::render_caption(&caption.profile, &caption.parties, &title),
+        court_heading: caption.profile.court_heading,
+        case_number: caption.parties.case_number,
+        plaintiffs: caption.parties.plaintiffs,
+        defendants: caption.parties.defendants,
+        signature_block,
+        certificate_of_service,
+    }))
+}
+
 async fn load_document_for_citation_workflow(
     state: &GatewayState,
     matter_document_id: uuid::Uuid,
@@ -1023,6 +1452,122 @@ pub(crate) async fn document_ready_handler(
     }))
 }
 
+/// Validate the matter's pleadings and filings against its jurisdiction's
+/// filing rules. The exhibit register and other matter files named
+/// `exhibit_*` only contribute exhibit labels.
+async fn validate_matter_filing_documents(
+    state: &GatewayState,
+    matter_id: &str,
+) -> Result<
+    (
+        Option<String>,
+        crate::legal::filing_validation::FilingValidationReport,
+    ),
+    (StatusCode, String),
+> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    crate::channels::web::server::ensure_matter_db_row_from_workspace(state, matter_id).await?;
+    crate::channels::web::server::backfill_matter_documents_from_workspace(state, matter_id)
+        .await?;
+    let jurisdiction = match store
+        .get_matter_db(&state.user_id, matter_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .and_then(|matter| matter.jurisdiction)
+    {
+        Some(jurisdiction) => Some(jurisdiction),
+        None => {
+            let matter_root = crate::channels::web::server::matter_root_for_gateway(state);
+            crate::channels::web::server::read_workspace_matter_metadata_optional(
+                state.workspace.as_ref(),
+                &matter_root,
+                matter_id,
+            )
+            .await
+            .and_then(|metadata| metadata.jurisdiction)
+        }
+    };
+    let ruleset =
+        crate::legal::filing_validation::ruleset_for_jurisdiction(jurisdiction.as_deref())
+            .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?;
+    let matter_prefix = crate::channels::web::server::matter_prefix_for_gateway(state, matter_id);
+
+    let (filings, others): (Vec<_>, Vec<_>) = store
+        .list_matter_documents_db(&state.user_id, matter_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .into_iter()
+        .partition(|document| is_package_filing(&matter_prefix, document));
+    let mut contents = Vec::with_capacity(filings.len());
+    for document in &filings {
+        match workspace.read(&document.path).await {
+            Ok(doc) => contents.push((document.path.as_str(), doc.content)),
+            Err(crate::error::WorkspaceError::DocumentNotFound { .. }) => {}
+            Err(err) => return Err((StatusCode::INTERNAL_SERVER_ERROR, err.to_string())),
+        }
+    }
+    let docs: Vec<crate::legal::filing_validation::FilingDocument<'_>> = contents
+        .iter()
+        .map(|(path, content)| crate::legal::filing_validation::FilingDocument { path, content })
+        .collect();
+    let mut filed_exhibits: Vec<String> = store
+        .list_matter_exhibits(&state.user_id, matter_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .into_iter()
+        .filter(|exhibit| exhibit.status == ExhibitStatus::Marked)
+        .map(|exhibit| exhibit.label)
+        .collect();
+    filed_exhibits.extend(others.iter().filter_map(|document| {
+        crate::legal::filing_validation::exhibit_label_from_path(&document.path)
+    }));
+    let report =
+        crate::legal::filing_validation::validate_filing_package(ruleset, &docs, &filed_exhibits);
+    Ok((jurisdiction, report))
+}
+
+pub(crate) async fn matter_filing_validation_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<MatterFilingValidationResponse>, (StatusCode, String)> {
+    let matter_id_guard = crate::channels::web::server::sanitize_matter_id_for_route(&id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id_guard,
+        &principal.user_id,
+        MatterMemberRole::Viewer,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let matter_root = crate::channels::web::server::matter_root_for_gateway(state.as_ref());
+    let matter_id = crate::channels::web::server::ensure_existing_matter_for_route(
+        workspace.as_ref(),
+        &matter_root,
+        &id,
+    )
+    .await?;
+    let (jurisdiction, report) =
+        validate_matter_filing_documents(state.as_ref(), &matter_id).await?;
+    Ok(Json(MatterFilingValidationResponse {
+        matter_id,
+        jurisdiction,
+        report,
+    }))
+}
+
 pub(crate) async fn matter_filing_package_handler(
     State(state): State<Arc<GatewayState>>,
     RequestPrincipal(principal): RequestPrincipal,
@@ -1050,6 +1595,24 @@ pub(crate) async fn matter_filing_package_handler(
     )
     .await?;
     ensure_ready_filing_documents(state.as_ref(), &matter_id).await?;
+    // Validation is advisory here: the report goes into the package index
+    // and the response, but does not block the export.
+    let validation = if state.store.is_some() {
+        Some(
+            validate_matter_filing_documents(state.as_ref(), &matter_id)
+                .await?
+                .1,
+        )
+    } else {
+        None
+    };
+    // The exhibit list is regenerated from the register, so labels match
+    // earlier packages.
+    let exhibits = if state.store.is_some() {
+        Some(super::exhibits::refresh_exhibit_list(state.as_ref(), &matter_id).await?)
+    } else {
+        None
+    };
     let matter_prefix = format!("{matter_root}/{matter_id}");
     let generated_at = Utc::now();
     let timestamp = generated_at.format("%Y%m%d-%H%M%S").to_string();
@@ -1195,6 +1758,34 @@ pub(crate) async fn matter_filing_package_handler(
         }
     }
 
+    if let Some((list_path, exhibits)) = &exhibits {
+        package.push_str("\n## Exhibit List\n\n");
+        if exhibits.is_empty() {
+            package.push_str("- No exhibits marked.\n");
+        } else {
+            package.push_str(&format!("Full list: `{list_path}`\n\n"));
+            for exhibit in exhibits {
+                package.push_str(&format!(
+                    "- Exhibit {}: `{}`{}\n",
+                    exhibit.label,
+                    exhibit.path,
+                    if exhibit.status == ExhibitStatus::Withdrawn {
+                        " (withdrawn)"
+                    } else {
+                        ""
+                    }
+                ));
+            }
+        }
+    }
+
+    if let Some(report) = &validation {
+        package.push('\n');
+        package.push_str(&crate::legal::filing_validation::render_validation_report(
+            report,
+        ));
+    }
+
     workspace
         .write(&destination, &package)
         .await
@@ -1207,6 +1798,252 @@ pub(crate) async fn matter_filing_package_handler(
             path: destination,
             generated_at: generated_at.to_rfc3339(),
             status: "created",
+            validation_passed: validation.map(|report| report.passed),
         }),
     ))
 }
+
+async fn load_matter_translation(
+    state: &GatewayState,
+    principal_user_id: &str,
+    raw_matter_id: &str,
+    raw_translation_id: &str,
+    role: MatterMemberRole,
+) -> Result<(String, crate::db::DocumentTranslationRecord), (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(raw_matter_id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        principal_user_id,
+        role,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    let translation_id =
+        crate::channels::web::server::parse_uuid(raw_translation_id, "translation_id")?;
+    let translation = store
+        .get_document_translation(&state.user_id, &matter_id, translation_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Translation not found".to_string()))?;
+    Ok((matter_id, translation))
+}
+
+pub(crate) async fn matter_translations_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<DocumentTranslationsResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(&id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Viewer,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    let translations = store
+        .list_document_translations(&state.user_id, &matter_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    Ok(Json(DocumentTranslationsResponse {
+        translations: translations
+            .into_iter()
+            .map(crate::channels::web::server::document_translation_record_to_info)
+            .collect(),
+    }))
+}
+
+pub(crate) async fn matter_translation_detail_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path((id, translation_id)): Path<(String, String)>,
+) -> Result<Json<DocumentTranslationDetailResponse>, (StatusCode, String)> {
+    let (_, translation) = load_matter_translation(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        &translation_id,
+        MatterMemberRole::Viewer,
+    )
+    .await?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    // Files may have been moved or deleted since the translation was recorded;
+    // show whatever is still present rather than failing the whole view.
+    let read = |path: String| async move { workspace.read(&path).await.ok().map(|d| d.content) };
+    let source_content = read(translation.source_path.clone()).await;
+    let machine_translation_content = read(translation.translation_path.clone()).await;
+    let certified_content = match translation.certified_path.clone() {
+        Some(path) => read(path).await,
+        None => None,
+    };
+    Ok(Json(DocumentTranslationDetailResponse {
+        translation: crate::channels::web::server::document_translation_record_to_info(translation),
+        source_content,
+        machine_translation_content,
+        certified_content,
+    }))
+}
+
+pub(crate) async fn matter_translation_request_certification_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path((id, translation_id)): Path<(String, String)>,
+    Json(req): Json<RequestTranslationCertificationRequest>,
+) -> Result<Json<DocumentTranslationResponse>, (StatusCode, String)> {
+    let (matter_id, translation) = load_matter_translation(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        &translation_id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    if translation.status == crate::db::TranslationStatus::Certified {
+        return Err((
+            StatusCode::CONFLICT,
+            "Translation is already certified".to_string(),
+        ));
+    }
+    let translator = req
+        .translator
+        .map(|raw| crate::channels::web::server::parse_required_matter_field("translator", &raw))
+        .transpose()?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let updated = store
+        .update_document_translation_status(
+            &state.user_id,
+            &matter_id,
+            translation.id,
+            &crate::db::UpdateDocumentTranslationStatusParams {
+                status: crate::db::TranslationStatus::CertificationRequested,
+                translator,
+                certified_path: None,
+            },
+        )
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Translation not found".to_string()))?;
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "translation_certification_requested",
+        state.user_id.as_str(),
+        Some(matter_id.as_str()),
+        crate::db::AuditSeverity::Info,
+        serde_json::json!({
+            "translation_id": updated.id.to_string(),
+            "source_path": updated.source_path,
+            "target_language": updated.target_language,
+            "translator": updated.translator,
+        }),
+    )
+    .await;
+    Ok(Json(DocumentTranslationResponse {
+        translation: crate::channels::web::server::document_translation_record_to_info(updated),
+    }))
+}
+
+pub(crate) async fn matter_translation_certify_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path((id, translation_id)): Path<(String, String)>,
+    Json(req): Json<CertifyTranslationRequest>,
+) -> Result<Json<DocumentTranslationResponse>, (StatusCode, String)> {
+    let (matter_id, translation) = load_matter_translation(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        &translation_id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    if translation.status == crate::db::TranslationStatus::Certified {
+        return Err((
+            StatusCode::CONFLICT,
+            "Translation is already certified".to_string(),
+        ));
+    }
+    let translator =
+        crate::channels::web::server::parse_required_matter_field("translator", &req.translator)?;
+    let certified_path = req.certified_path.trim().trim_matches('/').to_string();
+    let matter_root = crate::channels::web::server::matter_root_for_gateway(state.as_ref());
+    let matter_prefix = format!("{matter_root}/{matter_id}/");
+    if !certified_path.starts_with(&matter_prefix)
+        || certified_path.split('/').any(|part| part == "..")
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("certified_path must be a file under '{matter_prefix}'"),
+        ));
+    }
+    if crate::legal::translation::is_machine_translation_path(&certified_path) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "certified_path must point to the certified translation, not the machine draft"
+                .to_string(),
+        ));
+    }
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    if workspace.read(&certified_path).await.is_err() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("certified_path '{certified_path}' does not exist"),
+        ));
+    }
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let updated = store
+        .update_document_translation_status(
+            &state.user_id,
+            &matter_id,
+            translation.id,
+            &crate::db::UpdateDocumentTranslationStatusParams {
+                status: crate::db::TranslationStatus::Certified,
+                translator: Some(translator),
+                certified_path: Some(certified_path),
+            },
+        )
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Translation not found".to_string()))?;
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "translation_certified",
+        state.user_id.as_str(),
+        Some(matter_id.as_str()),
+        crate::db::AuditSeverity::Info,
+        serde_json::json!({
+            "translation_id": updated.id.to_string(),
+            "source_path": updated.source_path,
+            "target_language": updated.target_language,
+            "translator": updated.translator,
+            "certified_path": updated.certified_path,
+        }),
+    )
+    .await;
+    Ok(Json(DocumentTranslationResponse {
+        translation: crate::channels::web::server::document_translation_record_to_info(updated),
+    }))
+}
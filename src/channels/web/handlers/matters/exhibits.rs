@@ -0,0 +1,346 @@
+//! Matter exhibit register handlers.
+//!
+//! `GET /api/matters/{id}/exhibits` lists the register; `POST` marks a matter
+//! document with the next number for its party prefix and writes its cover
+//! sheet; `POST .../{exhibit_id}/withdraw` withdraws one without renumbering.
+//! Every change regenerates `exhibits/exhibit_list.md`.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::handlers::helpers::matter::db_matter_for_route;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{
+    AuditSeverity, CreateMatterExhibitParams, ExhibitStatus, MatterExhibitRecord, MatterMemberRole,
+};
+use crate::legal::exhibits::{
+    DEFAULT_PARTY_PREFIX, cover_sheet_path, exhibit_list_path, normalize_party_prefix,
+    render_cover_sheet, render_exhibit_list,
+};
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new()
+        .route(
+            "/api/matters/{id}/exhibits",
+            get(matter_exhibits_handler).post(matter_exhibit_mark_handler),
+        )
+        .route(
+            "/api/matters/{id}/exhibits/{exhibit_id}/withdraw",
+            post(matter_exhibit_withdraw_handler),
+        )
+}
+
+/// Rewrite the matter's exhibit list from the register and return the
+/// register with the list's path.
+pub(crate) async fn refresh_exhibit_list(
+    state: &GatewayState,
+    matter_id: &str,
+) -> Result<(String, Vec<MatterExhibitRecord>), (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let exhibits = store
+        .list_matter_exhibits(&state.user_id, matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let matter_prefix = crate::channels::web::server::matter_prefix_for_gateway(state, matter_id);
+    let path = exhibit_list_path(&matter_prefix);
+    if !exhibits.is_empty() || workspace.read(&path).await.is_ok() {
+        workspace
+            .write(
+                &path,
+                &render_exhibit_list(matter_id, &exhibits, Utc::now()),
+            )
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    }
+    Ok((path, exhibits))
+}
+
+/// Caption block titled `title` and the case number, when the matter has a
+/// client on file.
+pub(crate) async fn matter_caption_block(
+    state: &GatewayState,
+    matter_id: &str,
+    title: &str,
+) -> Result<(Option<String>, Option<String>), (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let Some(matter) = store
+        .get_matter_db(&state.user_id, matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    else {
+        return Ok((None, None));
+    };
+    let Some(client) = store
+        .get_client(&state.user_id, matter.client_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    else {
+        return Ok((None, None));
+    };
+    let parties = store
+        .list_matter_parties(matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let caption = crate::legal::caption::matter_caption(&matter, &client, &parties, None);
+    Ok((
+        Some(crate::legal::caption::render_caption(
+            &caption.profile,
+            &caption.parties,
+            title,
+        )),
+        caption.parties.case_number,
+    ))
+}
+
+pub(crate) async fn matter_exhibits_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<MatterExhibitsResponse>, (StatusCode, String)> {
+    let matter_id = db_matter_for_route(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Viewer,
+    )
+    .await?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let matter_prefix =
+        crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), &matter_id);
+    let exhibits = store
+        .list_matter_exhibits(&state.user_id, &matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .into_iter()
+        .map(|record| {
+            crate::channels::web::server::matter_exhibit_record_to_info(record, &matter_prefix)
+        })
+        .collect();
+    Ok(Json(MatterExhibitsResponse {
+        exhibit_list_path: exhibit_list_path(&matter_prefix),
+        matter_id,
+        exhibits,
+    }))
+}
+
+pub(crate) async fn matter_exhibit_mark_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<MarkExhibitRequest>,
+) -> Result<(StatusCode, Json<MatterExhibitResponse>), (StatusCode, String)> {
+    let matter_id = db_matter_for_route(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let party_prefix =
+        normalize_party_prefix(req.party_prefix.as_deref().unwrap_or(DEFAULT_PARTY_PREFIX))
+            .map_err(|err| (StatusCode::BAD_REQUEST, err))?;
+    let description = crate::channels::web::server::parse_optional_matter_field(req.description);
+    crate::channels::web::server::validate_optional_matter_field_length(
+        "description",
+        &description,
+    )?;
+    let matter_prefix =
+        crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), &matter_id);
+
+    crate::channels::web::server::backfill_matter_documents_from_workspace(
+        state.as_ref(),
+        &matter_id,
+    )
+    .await?;
+    let document = match (req.document_id.as_deref(), req.path.as_deref()) {
+        (Some(raw_id), _) => {
+            let document_id = Uuid::parse_str(raw_id.trim()).map_err(|_| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    "'document_id' must be a UUID".to_string(),
+                )
+            })?;
+            store
+                .get_matter_document(&state.user_id, &matter_id, document_id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+                .ok_or((StatusCode::NOT_FOUND, "Document not found".to_string()))?
+        }
+        (None, Some(raw_path)) => {
+            let path = raw_path.trim().trim_start_matches('/');
+            if !path.starts_with(&format!("{matter_prefix}/")) || path.contains("..") {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!("'path' must be a document under '{matter_prefix}/'"),
+                ));
+            }
+            store
+                .list_matter_documents_db(&state.user_id, &matter_id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+                .into_iter()
+                .find(|document| document.path == path)
+                .ok_or((StatusCode::NOT_FOUND, "Document not found".to_string()))?
+        }
+        (None, None) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "'document_id' or 'path' is required".to_string(),
+            ));
+        }
+    };
+
+    let existing = store
+        .list_matter_exhibits(&state.user_id, &matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if let Some(marked) = existing
+        .iter()
+        .find(|exhibit| exhibit.status == ExhibitStatus::Marked && exhibit.path == document.path)
+    {
+        return Err((
+            StatusCode::CONFLICT,
+            format!(
+                "'{}' is already marked as Exhibit {}",
+                document.path, marked.label
+            ),
+        ));
+    }
+
+    let exhibit = store
+        .create_matter_exhibit(
+            &state.user_id,
+            &matter_id,
+            &CreateMatterExhibitParams {
+                party_prefix,
+                path: document.path.clone(),
+                matter_document_id: Some(document.id),
+                description: description.or_else(|| Some(document.display_name.clone())),
+                created_by: principal.user_id.clone(),
+            },
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let (caption, case_number) = matter_caption_block(
+        state.as_ref(),
+        &matter_id,
+        &format!("Exhibit {}", exhibit.label),
+    )
+    .await?;
+    let cover_path = cover_sheet_path(&matter_prefix, &exhibit.label);
+    workspace
+        .write(
+            &cover_path,
+            &render_cover_sheet(&exhibit, caption.as_deref(), case_number.as_deref()),
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let (exhibit_list_path, _) = refresh_exhibit_list(state.as_ref(), &matter_id).await?;
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "exhibit_marked",
+        &principal.user_id,
+        Some(matter_id.as_str()),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "exhibit_id": exhibit.id.to_string(),
+            "label": exhibit.label.clone(),
+            "path": exhibit.path.clone(),
+        }),
+    )
+    .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(MatterExhibitResponse {
+            exhibit: crate::channels::web::server::matter_exhibit_record_to_info(
+                exhibit,
+                &matter_prefix,
+            ),
+            exhibit_list_path,
+        }),
+    ))
+}
+
+pub(crate) async fn matter_exhibit_withdraw_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path((id, exhibit_id)): Path<(String, String)>,
+) -> Result<Json<MatterExhibitResponse>, (StatusCode, String)> {
+    let matter_id = db_matter_for_route(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let exhibit_id = Uuid::parse_str(&exhibit_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid exhibit id".to_string()))?;
+    let exhibit = store
+        .withdraw_matter_exhibit(&state.user_id, &matter_id, exhibit_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Exhibit not found".to_string()))?;
+    let (exhibit_list_path, _) = refresh_exhibit_list(state.as_ref(), &matter_id).await?;
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "exhibit_withdrawn",
+        &principal.user_id,
+        Some(matter_id.as_str()),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "exhibit_id": exhibit.id.to_string(),
+            "label": exhibit.label.clone(),
+            "path": exhibit.path.clone(),
+        }),
+    )
+    .await;
+
+    let matter_prefix =
+        crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), &matter_id);
+    Ok(Json(MatterExhibitResponse {
+        exhibit: crate::channels::web::server::matter_exhibit_record_to_info(
+            exhibit,
+            &matter_prefix,
+        ),
+        exhibit_list_path,
+    }))
+}
@@ -0,0 +1,245 @@
+//! Immigration form handlers.
+//!
+//! `GET /api/matters/{id}/immigration/forms/{form_id}` fills a USCIS form
+//! field set from the matter's data (see [`crate::legal::immigration`]) and
+//! reports missing and invalid fields. `POST .../export` writes the filled
+//! values under `immigration/forms/` as JSON or as an XFDF overlay.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::handlers::helpers::matter::matter_for_route;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{AuditSeverity, MatterMemberRole};
+use crate::legal::immigration::{
+    ImmigrationForm, case_data_path, fill_form, flatten_json, form_export_path,
+    get_immigration_form, parse_case_data, render_xfdf,
+};
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new()
+        .route(
+            "/api/matters/{id}/immigration/forms/{form_id}",
+            get(matter_immigration_form_handler),
+        )
+        .route(
+            "/api/matters/{id}/immigration/forms/{form_id}/export",
+            post(matter_immigration_form_export_handler),
+        )
+}
+
+fn immigration_form_for_route(
+    form_id: &str,
+) -> Result<&'static ImmigrationForm, (StatusCode, String)> {
+    get_immigration_form(form_id)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            format!("Unknown immigration form '{form_id}'"),
+        ))
+}
+
+/// Data keys for form filling: `matter.*` from `matter.yaml`, `client.*`
+/// and `custom.*` from the database when available, then the case data
+/// file, which wins on conflicts.
+async fn immigration_form_data(
+    state: &GatewayState,
+    matter_id: &str,
+) -> Result<BTreeMap<String, String>, (StatusCode, String)> {
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let matter_root = crate::channels::web::server::matter_root_for_gateway(state);
+    let metadata = crate::legal::matter::read_matter_metadata_for_root(
+        workspace.as_ref(),
+        &matter_root,
+        matter_id,
+    )
+    .await
+    .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()))?;
+
+    let mut data = BTreeMap::new();
+    let mut insert = |key: &str, value: Option<&str>| {
+        if let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) {
+            data.insert(key.to_string(), value.to_string());
+        }
+    };
+    insert("matter.id", Some(metadata.matter_id.as_str()));
+    insert("matter.client", Some(metadata.client.as_str()));
+    insert("matter.jurisdiction", metadata.jurisdiction.as_deref());
+    insert("matter.practice_area", metadata.practice_area.as_deref());
+    insert("matter.opened_date", metadata.opened_date.as_deref());
+    insert("client.name", Some(metadata.client.as_str()));
+
+    if let Some(store) = state.store.as_ref()
+        && let Some(matter) = store
+            .get_matter_db(&state.user_id, matter_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    {
+        if let Some(client) = store
+            .get_client(&state.user_id, matter.client_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        {
+            insert("client.name", Some(client.name.as_str()));
+            insert("client.email", client.email.as_deref());
+            insert("client.phone", client.phone.as_deref());
+            insert("client.address", client.address.as_deref());
+        }
+        flatten_json("custom", &matter.custom_fields, &mut data);
+    }
+
+    let matter_prefix = crate::channels::web::server::matter_prefix_for_gateway(state, matter_id);
+    if let Ok(doc) = workspace.read(&case_data_path(&matter_prefix)).await {
+        let case_data = parse_case_data(&doc.content).map_err(|e| {
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!("{}: {e}", doc.path),
+            )
+        })?;
+        data.extend(case_data);
+    }
+    Ok(data)
+}
+
+fn deadline_presets(form: &ImmigrationForm) -> Result<Vec<CourtRuleInfo>, (StatusCode, String)> {
+    let mut presets = Vec::with_capacity(form.deadline_presets.len());
+    for rule_id in &form.deadline_presets {
+        if let Some(rule) = crate::legal::calendar::get_court_rule(rule_id)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        {
+            presets.push(crate::channels::web::handlers::legal::court_rule_to_info(
+                &rule,
+            ));
+        }
+    }
+    Ok(presets)
+}
+
+pub(crate) async fn matter_immigration_form_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path((id, form_id)): Path<(String, String)>,
+) -> Result<Json<MatterImmigrationFormResponse>, (StatusCode, String)> {
+    let matter_id = matter_for_route(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Viewer,
+    )
+    .await?;
+    let form = immigration_form_for_route(&form_id)?;
+    let data = immigration_form_data(state.as_ref(), &matter_id).await?;
+    let matter_prefix =
+        crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), &matter_id);
+    Ok(Json(MatterImmigrationFormResponse {
+        matter_id,
+        case_data_path: case_data_path(&matter_prefix),
+        form: fill_form(form, &data),
+        deadline_presets: deadline_presets(form)?,
+    }))
+}
+
+pub(crate) async fn matter_immigration_form_export_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path((id, form_id)): Path<(String, String)>,
+    Json(req): Json<ImmigrationFormExportRequest>,
+) -> Result<(StatusCode, Json<ImmigrationFormExportResponse>), (StatusCode, String)> {
+    let matter_id = matter_for_route(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let format = req
+        .format
+        .as_deref()
+        .map(|value| value.trim().to_ascii_lowercase())
+        .unwrap_or_else(|| "json".to_string());
+    if format != "json" && format != "xfdf" {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'format' must be 'json' or 'xfdf'".to_string(),
+        ));
+    }
+    let form = immigration_form_for_route(&form_id)?;
+    let data = immigration_form_data(state.as_ref(), &matter_id).await?;
+    let filled = fill_form(form, &data);
+    if !filled.complete && !req.allow_incomplete {
+        let mut problems = filled.missing.clone();
+        problems.extend(filled.invalid.iter().cloned());
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!(
+                "{} is incomplete: {}. Pass allow_incomplete to export anyway.",
+                form.id.to_ascii_uppercase(),
+                problems.join("; ")
+            ),
+        ));
+    }
+
+    let content = if format == "xfdf" {
+        render_xfdf(&filled)
+    } else {
+        serde_json::to_string_pretty(&filled)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    };
+    let matter_prefix =
+        crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), &matter_id);
+    let path = form_export_path(&matter_prefix, &form.id, &format);
+    workspace
+        .write(&path, &content)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "immigration_form_exported",
+        &principal.user_id,
+        Some(matter_id.as_str()),
+        if filled.complete {
+            AuditSeverity::Info
+        } else {
+            AuditSeverity::Warn
+        },
+        serde_json::json!({
+            "form_id": form.id,
+            "format": format,
+            "path": path,
+            "complete": filled.complete,
+            "missing": filled.missing.len(),
+            "invalid": filled.invalid.len(),
+        }),
+    )
+    .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ImmigrationFormExportResponse {
+            matter_id,
+            form_id: form.id.clone(),
+            format,
+            path,
+            complete: filled.complete,
+            missing: filled.missing,
+            invalid: filled.invalid,
+        }),
+    ))
+}
@@ -0,0 +1,298 @@
+//! IP docketing handler.
+//!
+//! `POST /api/matters/{id}/deadlines/ip-docket` computes the statutory
+//! patent or trademark terms for one application from its event dates (see
+//! [`crate::legal::ip_docket`]). With `save = true` each event becomes a
+//! completed anchor deadline and every term is created with `computed_from`
+//! pointing at its anchor, so patching an anchor's date recomputes the terms
+//! and their reminder routines through the normal deadline cascade.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::post,
+};
+
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::handlers::helpers::matter::matter_for_route;
+use crate::channels::web::handlers::matters::core::deadline_added_event;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{
+    AuditSeverity, CreateMatterDeadlineParams, MatterDeadlineRecord, MatterDeadlineType,
+    MatterMemberRole,
+};
+use crate::legal::ip_docket::{
+    IpRight, IpTrigger, anchor_rule_ref, application_label, plan_ip_docket, resolve_ip_office,
+    trigger_instant,
+};
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new().route(
+        "/api/matters/{id}/deadlines/ip-docket",
+        post(matter_ip_docket_handler),
+    )
+}
+
+fn ip_docket_events(
+    req: &IpDocketRequest,
+) -> Result<BTreeMap<IpTrigger, chrono::NaiveDate>, (StatusCode, String)> {
+    let mut events = BTreeMap::new();
+    for trigger in IpTrigger::ALL {
+        let raw = match trigger {
+            IpTrigger::FilingDate => &req.filing_date,
+            IpTrigger::PriorityDate => &req.priority_date,
+            IpTrigger::GrantDate => &req.grant_date,
+            IpTrigger::RegistrationDate => &req.registration_date,
+            IpTrigger::OfficeActionDate => &req.office_action_date,
+            IpTrigger::AllowanceDate => &req.allowance_date,
+        };
+        let Some(raw) = raw
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        else {
+            continue;
+        };
+        let parsed = crate::channels::web::server::parse_datetime_value(trigger.as_str(), raw)?;
+        events.insert(trigger, parsed.date_naive());
+    }
+    if events.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "At least one application event date is required".to_string(),
+        ));
+    }
+    Ok(events)
+}
+
+fn explanation_rule_id(record: &MatterDeadlineRecord) -> Option<&str> {
+    record
+        .explanation
+        .as_ref()
+        .and_then(|value| value.get("rule_id"))
+        .and_then(|value| value.as_str())
+}
+
+pub(crate) async fn matter_ip_docket_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<IpDocketRequest>,
+) -> Result<Json<IpDocketResponse>, (StatusCode, String)> {
+    // Saving deadlines requires Collaborator access; a preview only needs Viewer.
+    let minimum_role = if req.save {
+        MatterMemberRole::Collaborator
+    } else {
+        MatterMemberRole::Viewer
+    };
+    let matter_id = matter_for_route(state.as_ref(), &principal.user_id, &id, minimum_role).await?;
+
+    let office = resolve_ip_office(&req.office).ok_or((
+        StatusCode::BAD_REQUEST,
+        format!("Unknown IP office '{}'", req.office.trim()),
+    ))?;
+    let right = IpRight::parse(&req.right).ok_or((
+        StatusCode::BAD_REQUEST,
+        "'right' must be 'patent' or 'trademark'".to_string(),
+    ))?;
+    let application_number =
+        crate::channels::web::server::parse_optional_matter_field(req.application_number.clone());
+    crate::channels::web::server::validate_optional_matter_field_length(
+        "application_number",
+        &application_number,
+    )?;
+    let events = ip_docket_events(&req)?;
+    let reminder_days = crate::channels::web::server::normalize_reminder_days(&req.reminder_days)?;
+    let plan = plan_ip_docket(office, right, &events, req.include_treaty.unwrap_or(true))
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?;
+    let label = application_label(office, right, application_number.as_deref());
+
+    if req.save && state.store.is_none() {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Database not available".to_string(),
+        ));
+    }
+
+    // Anchors already docketed for this application, keyed by trigger event.
+    let mut anchors: BTreeMap<IpTrigger, MatterDeadlineRecord> = BTreeMap::new();
+    if let Some(store) = state.store.as_ref() {
+        let existing = store
+            .list_matter_deadlines(&state.user_id, &matter_id)
+            .await
+            .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+        for (trigger, date) in &events {
+            let anchor_ref =
+                anchor_rule_ref(office, right, *trigger, application_number.as_deref());
+            let Some(anchor) = existing
+                .iter()
+                .find(|record| record.rule_ref.as_deref() == Some(anchor_ref.as_str()))
+            else {
+                continue;
+            };
+            if anchor.due_at.date_naive() != *date {
+                if req.save {
+                    return Err((
+                        StatusCode::CONFLICT,
+                        format!(
+                            "{label} {} is docketed as {} (deadline {}). Patch that deadline to move its dependent terms.",
+                            trigger.label(),
+                            anchor.due_at.date_naive(),
+                            anchor.id
+                        ),
+                    ));
+                }
+                continue;
+            }
+            anchors.insert(*trigger, anchor.clone());
+        }
+    }
+
+    let mut saved_anchors = Vec::new();
+    if req.save {
+        let store = state.store.as_ref().ok_or((
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Database not available".to_string(),
+        ))?;
+        crate::channels::web::server::ensure_matter_db_row_from_workspace(
+            state.as_ref(),
+            &matter_id,
+        )
+        .await?;
+        for item in &plan {
+            let trigger = item.meta.trigger;
+            if anchors.contains_key(&trigger) {
+                continue;
+            }
+            let due_at = trigger_instant(item.trigger_date);
+            let anchor = store
+                .create_matter_deadline(
+                    &state.user_id,
+                    &matter_id,
+                    &CreateMatterDeadlineParams {
+                        title: format!("{label}: {}", trigger.label()),
+                        deadline_type: MatterDeadlineType::Internal,
+                        due_at,
+                        completed_at: Some(due_at),
+                        reminder_days: Vec::new(),
+                        rule_ref: Some(anchor_rule_ref(
+                            office,
+                            right,
+                            trigger,
+                            application_number.as_deref(),
+                        )),
+                        computed_from: None,
+                        task_id: None,
+                        explanation: None,
+                        rule_version: None,
+                        is_unsupported: false,
+                    },
+                )
+                .await
+                .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+            state.publish_event(deadline_added_event(&anchor, &principal.user_id));
+            saved_anchors.push(crate::channels::web::server::deadline_record_to_info(
+                anchor.clone(),
+            ));
+            anchors.insert(trigger, anchor);
+        }
+    }
+
+    // Terms already docketed from each anchor, keyed by rule id.
+    let mut docketed: HashMap<(IpTrigger, String), uuid::Uuid> = HashMap::new();
+    if let Some(store) = state.store.as_ref() {
+        for (trigger, anchor) in &anchors {
+            let dependents = store
+                .list_deadlines_by_trigger(&state.user_id, &matter_id, anchor.id)
+                .await
+                .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+            for dep in dependents {
+                if let Some(rule_id) = explanation_rule_id(&dep) {
+                    docketed.insert((*trigger, rule_id.to_string()), dep.id);
+                }
+            }
+        }
+    }
+
+    let mut entries = Vec::with_capacity(plan.len());
+    let mut saved = Vec::new();
+    for item in &plan {
+        let trigger = item.meta.trigger;
+        let anchor_id = anchors.get(&trigger).map(|anchor| anchor.id);
+        let (params, trace) = crate::legal::calendar::deadline_from_rule_with_trace(
+            &format!("{label}: {}", item.meta.title),
+            &item.rule,
+            trigger_instant(item.trigger_date),
+            reminder_days.clone(),
+            anchor_id,
+            None,
+        );
+        let existing = docketed.get(&(trigger, item.meta.id.clone())).copied();
+
+        if req.save
+            && existing.is_none()
+            && let Some(store) = state.store.as_ref()
+        {
+            let record = store
+                .create_matter_deadline(&state.user_id, &matter_id, &params)
+                .await
+                .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+            crate::channels::web::server::sync_deadline_reminder_routines_for_record(
+                state.as_ref(),
+                &record,
+            )
+            .await?;
+            state.publish_event(deadline_added_event(&record, &principal.user_id));
+            saved.push(crate::channels::web::server::deadline_record_to_info(
+                record,
+            ));
+        }
+
+        let explanation = serde_json::to_value(&trace).unwrap_or(serde_json::Value::Null);
+        entries.push(IpDocketEntryInfo {
+            rule: crate::channels::web::handlers::legal::court_rule_to_info(&item.rule),
+            trigger: trigger.as_str().to_string(),
+            trigger_date: item.trigger_date.to_string(),
+            grace_for: item.meta.grace_for.clone(),
+            deadline: crate::channels::web::server::deadline_compute_preview_from_params(
+                &params,
+                explanation,
+            ),
+            existing_deadline_id: existing.map(|value| value.to_string()),
+        });
+    }
+
+    if req.save {
+        crate::channels::web::server::record_legal_audit_event(
+            state.as_ref(),
+            "ip_docket_saved",
+            &principal.user_id,
+            Some(matter_id.as_str()),
+            AuditSeverity::Info,
+            serde_json::json!({
+                "office": office.code,
+                "right": right.as_str(),
+                "application": label,
+                "anchors_created": saved_anchors.len(),
+                "deadlines_created": saved.len(),
+                "already_docketed": entries.len() - saved.len(),
+            }),
+        )
+        .await;
+    }
+
+    Ok(Json(IpDocketResponse {
+        matter_id,
+        office: office.code.to_string(),
+        right: right.as_str().to_string(),
+        application: label,
+        entries,
+        anchors: saved_anchors,
+        saved,
+    }))
+}
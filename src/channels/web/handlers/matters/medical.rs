@@ -0,0 +1,154 @@
+//! Medical records review handlers.
+//!
+//! `GET /api/matters/{id}/medical-chronology` extracts visits from the text
+//! records under `medical/records/` (see [`crate::legal::medical_records`]).
+//! `POST` writes the treating-provider chronology and the structured visits
+//! next to the matter's records tracker.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::get,
+};
+use chrono::Utc;
+
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::handlers::helpers::matter::matter_for_route;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::MatterMemberRole;
+use crate::legal::medical_records::{
+    MedicalVisit, extract_visits, medical_records_prefix, medical_visits_path, provider_summaries,
+    render_treatment_chronology, sort_visits, treatment_chronology_path,
+};
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new().route(
+        "/api/matters/{id}/medical-chronology",
+        get(medical_chronology_handler).post(medical_chronology_export_handler),
+    )
+}
+
+struct ExtractedRecords {
+    records_scanned: usize,
+    records_without_visits: Vec<String>,
+    visits: Vec<MedicalVisit>,
+}
+
+async fn extract_matter_visits(
+    state: &GatewayState,
+    matter_id: &str,
+) -> Result<ExtractedRecords, (StatusCode, String)> {
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let matter_prefix = crate::channels::web::server::matter_prefix_for_gateway(state, matter_id);
+    let records = crate::channels::web::server::list_matter_documents_recursive(
+        workspace.as_ref(),
+        &medical_records_prefix(&matter_prefix),
+        false,
+    )
+    .await?;
+
+    let mut extracted = ExtractedRecords {
+        records_scanned: 0,
+        records_without_visits: Vec::new(),
+        visits: Vec::new(),
+    };
+    for record in records.into_iter().filter(|record| !record.is_dir) {
+        let doc = workspace
+            .read(&record.path)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        extracted.records_scanned += 1;
+        let visits = extract_visits(&doc.path, &doc.content);
+        if visits.is_empty() {
+            extracted.records_without_visits.push(doc.path);
+        }
+        extracted.visits.extend(visits);
+    }
+    extracted.records_without_visits.sort();
+    sort_visits(&mut extracted.visits);
+    Ok(extracted)
+}
+
+pub(crate) async fn medical_chronology_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<MedicalChronologyResponse>, (StatusCode, String)> {
+    let matter_id = matter_for_route(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Viewer,
+    )
+    .await?;
+    let extracted = extract_matter_visits(state.as_ref(), &matter_id).await?;
+    Ok(Json(MedicalChronologyResponse {
+        matter_id,
+        records_scanned: extracted.records_scanned,
+        records_without_visits: extracted.records_without_visits,
+        providers: provider_summaries(&extracted.visits),
+        visits: extracted.visits,
+    }))
+}
+
+pub(crate) async fn medical_chronology_export_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<(StatusCode, Json<MedicalChronologyExportResponse>), (StatusCode, String)> {
+    let matter_id = matter_for_route(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let extracted = extract_matter_visits(state.as_ref(), &matter_id).await?;
+
+    let generated_at = Utc::now();
+    let matter_prefix =
+        crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), &matter_id);
+    let path = treatment_chronology_path(&matter_prefix);
+    let data_path = medical_visits_path(&matter_prefix);
+    workspace
+        .write(
+            &path,
+            &render_treatment_chronology(
+                &matter_id,
+                &extracted.visits,
+                &extracted.records_without_visits,
+                generated_at,
+            ),
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let data = serde_json::to_string_pretty(&extracted.visits)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    workspace
+        .write(&data_path, &data)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(MedicalChronologyExportResponse {
+            matter_id,
+            path,
+            data_path,
+            generated_at: generated_at.to_rfc3339(),
+            visit_count: extracted.visits.len(),
+            provider_count: provider_summaries(&extracted.visits).len(),
+        }),
+    ))
+}
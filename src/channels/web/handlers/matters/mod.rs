@@ -1,9 +1,20 @@
 //! Matter-related web handlers.
 
+pub mod client_comms;
+pub mod closeout;
+pub mod compare;
 pub mod conflicts;
 pub mod core;
 pub mod documents;
+pub mod exhibits;
 pub mod finance;
+pub mod immigration;
+pub mod ip_docket;
+pub mod medical;
+pub mod precedents;
+pub mod settlement;
+pub mod status_reports;
+pub mod timeline;
 pub mod work;
 
 use std::sync::Arc;
@@ -15,8 +26,19 @@ use crate::channels::web::state::GatewayState;
 pub fn routes() -> Router<Arc<GatewayState>> {
     Router::new()
         .merge(core::routes())
+        .merge(client_comms::routes())
         .merge(documents::routes())
+        .merge(compare::routes())
+        .merge(exhibits::routes())
         .merge(finance::routes())
+        .merge(immigration::routes())
+        .merge(ip_docket::routes())
+        .merge(medical::routes())
+        .merge(precedents::routes())
+        .merge(settlement::routes())
+        .merge(status_reports::routes())
+        .merge(timeline::routes())
         .merge(work::routes())
         .merge(conflicts::routes())
+        .merge(closeout::routes())
 }
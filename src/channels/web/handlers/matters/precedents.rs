@@ -0,0 +1,349 @@
+//! Precedent bank handlers.
+//!
+//! `POST /api/matters/{id}/precedents/anonymize` previews the anonymization
+//! mapping for a matter document; `POST /api/matters/{id}/precedents`
+//! promotes it from a closed matter into the firm precedent bank once that
+//! mapping has been reviewed; `GET /api/matters/{id}/precedents/suggest`
+//! proposes precedent for drafting in a matter; `GET /api/precedents` lists
+//! the bank. Listing and suggestions leave out precedent whose source matter
+//! the requester cannot access.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::handlers::helpers::matter::require_matter_access;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{AuditSeverity, MatterMemberRole, MatterStatus};
+use crate::legal::anonymize::{propose_replacements, review_token, review_token_matches};
+use crate::legal::precedent::{
+    PrecedentPromotion, Replacement, apply_replacements, default_replacements, promote_precedent,
+    query_terms, suggest_precedents, visible_precedents,
+};
+
+const DEFAULT_SUGGESTION_LIMIT: usize = 5;
+const MAX_SUGGESTION_LIMIT: usize = 20;
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new()
+        .route("/api/precedents", get(precedents_list_handler))
+        .route(
+            "/api/matters/{id}/precedents",
+            post(matter_precedent_promote_handler),
+        )
+        .route(
+            "/api/matters/{id}/precedents/anonymize",
+            post(matter_precedent_anonymize_handler),
+        )
+        .route(
+            "/api/matters/{id}/precedents/suggest",
+            get(matter_precedent_suggest_handler),
+        )
+}
+
+fn validate_replacements(replacements: &[Replacement]) -> Result<(), (StatusCode, String)> {
+    if replacements
+        .iter()
+        .any(|r| r.original.trim().is_empty() || r.placeholder.trim().is_empty())
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "each replacement needs a non-empty 'original' and 'placeholder'".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) async fn precedents_list_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+) -> Result<Json<PrecedentsResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let precedents = visible_precedents(store.as_ref(), &state.user_id, &principal.user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(PrecedentsResponse {
+        precedents: precedents
+            .into_iter()
+            .map(crate::channels::web::server::precedent_record_to_info)
+            .collect(),
+    }))
+}
+
+pub(crate) async fn matter_precedent_promote_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<PromotePrecedentRequest>,
+) -> Result<(StatusCode, Json<PromotePrecedentResponse>), (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(&id)?;
+    // Promotion publishes work product firm-wide, so only the matter owner
+    // may do it.
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Owner,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    let matter = store
+        .get_matter_db(&state.user_id, &matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Matter not found".to_string()))?;
+    if matter.status != MatterStatus::Closed {
+        return Err((
+            StatusCode::CONFLICT,
+            format!("Matter '{matter_id}' must be closed before its work product is promoted"),
+        ));
+    }
+
+    let matter_prefix =
+        crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), &matter_id);
+    let source_path =
+        crate::channels::web::server::matter_document_path("path", &req.path, &matter_prefix)?;
+    let title = crate::channels::web::server::parse_optional_matter_field(req.title);
+    crate::channels::web::server::validate_optional_matter_field_length("title", &title)?;
+    let title = title.unwrap_or_else(|| {
+        let name = source_path.rsplit('/').next().unwrap_or(&source_path);
+        name.rsplit_once('.')
+            .map_or(name, |(stem, _)| stem)
+            .to_string()
+    });
+    let document_type =
+        crate::channels::web::server::parse_optional_matter_field(req.document_type);
+    crate::channels::web::server::validate_optional_matter_field_length(
+        "document_type",
+        &document_type,
+    )?;
+    validate_replacements(&req.replacements)?;
+    let reviewed_token = req.review_token.as_deref().map(str::trim).ok_or((
+        StatusCode::BAD_REQUEST,
+        "'review_token' is required: review the mapping with \
+         POST /api/matters/{id}/precedents/anonymize first"
+            .to_string(),
+    ))?;
+
+    let source = workspace.read(&source_path).await.map_err(|_| {
+        (
+            StatusCode::NOT_FOUND,
+            format!("Document '{source_path}' not found"),
+        )
+    })?;
+    if !review_token_matches(
+        &state.review_token_key,
+        &principal.user_id,
+        &source.content,
+        &req.replacements,
+        reviewed_token,
+    ) {
+        return Err((
+            StatusCode::CONFLICT,
+            "The document or the anonymization mapping changed since you reviewed it; \
+             preview it again"
+                .to_string(),
+        ));
+    }
+    let client = store
+        .get_client(&state.user_id, matter.client_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let mut replacements = default_replacements(client.as_ref().map(|c| c.name.as_str()));
+    replacements.extend(req.replacements.iter().cloned());
+
+    let (record, substitutions) = promote_precedent(
+        store.as_ref(),
+        workspace.as_ref(),
+        &state.user_id,
+        &PrecedentPromotion {
+            matter: &matter,
+            source_path: &source_path,
+            source_content: &source.content,
+            title: &title,
+            document_type: document_type.as_deref(),
+            replacements: &replacements,
+            promoted_by: &principal.user_id,
+        },
+    )
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "precedent_promoted",
+        &principal.user_id,
+        Some(&matter_id),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "precedent_id": record.id.to_string(),
+            "source_path": source_path,
+            "precedent_path": record.path,
+            "substitutions": substitutions,
+            "reviewed_replacements": req.replacements.len(),
+            "review_token": reviewed_token,
+        }),
+    )
+    .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(PromotePrecedentResponse {
+            precedent: crate::channels::web::server::precedent_record_to_info(record),
+            substitutions,
+        }),
+    ))
+}
+
+pub(crate) async fn matter_precedent_anonymize_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<AnonymizePrecedentRequest>,
+) -> Result<Json<AnonymizePrecedentResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(&id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Owner,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    let matter = store
+        .get_matter_db(&state.user_id, &matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Matter not found".to_string()))?;
+    let matter_prefix =
+        crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), &matter_id);
+    let source_path =
+        crate::channels::web::server::matter_document_path("path", &req.path, &matter_prefix)?;
+    if let Some(replacements) = req.replacements.as_deref() {
+        validate_replacements(replacements)?;
+    }
+
+    let source = workspace.read(&source_path).await.map_err(|_| {
+        (
+            StatusCode::NOT_FOUND,
+            format!("Document '{source_path}' not found"),
+        )
+    })?;
+    let client = store
+        .get_client(&state.user_id, matter.client_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let client_name = client.as_ref().map(|c| c.name.as_str());
+    let indexed = store
+        .list_document_entities(&state.user_id, &matter_id, None)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let proposed = propose_replacements(&source.content, &source_path, client_name, &indexed);
+    let replacements = req
+        .replacements
+        .unwrap_or_else(|| proposed.iter().map(Replacement::from).collect());
+
+    // Preview exactly what promotion applies: the client's name plus the
+    // mapping under review.
+    let mut applied = default_replacements(client_name);
+    applied.extend(replacements.iter().cloned());
+    let (preview, substitutions) = apply_replacements(&source.content, &applied);
+    Ok(Json(AnonymizePrecedentResponse {
+        matter_id,
+        review_token: review_token(
+            &state.review_token_key,
+            &principal.user_id,
+            &source.content,
+            &replacements,
+        ),
+        path: source_path,
+        proposed,
+        replacements,
+        substitutions,
+        preview,
+    }))
+}
+
+pub(crate) async fn matter_precedent_suggest_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Query(query): Query<PrecedentSuggestQuery>,
+) -> Result<Json<PrecedentSuggestResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(&id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Viewer,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    if query_terms(&query.q).is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'q' must contain at least one search term".to_string(),
+        ));
+    }
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_SUGGESTION_LIMIT)
+        .clamp(1, MAX_SUGGESTION_LIMIT);
+    let practice_area = store
+        .get_matter_db(&state.user_id, &matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .and_then(|matter| matter.practice_area);
+
+    let suggestions = suggest_precedents(
+        store.as_ref(),
+        workspace.as_ref(),
+        &state.user_id,
+        &principal.user_id,
+        &query.q,
+        practice_area.as_deref(),
+        limit,
+    )
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(PrecedentSuggestResponse {
+        matter_id,
+        suggestions,
+    }))
+}
@@ -0,0 +1,479 @@
+//! Settlement negotiation handlers.
+//!
+//! `GET /api/matters/{id}/settlement` lists demands and offers with the
+//! current gap and client authority. `POST .../settlement/proposals`
+//! records a proposal and warns (without blocking) when a client proposal
+//! goes past the authority in force; `POST .../settlement/authority`
+//! records a new grant and `POST .../settlement/check` tests a proposed
+//! response before it is sent. `POST .../settlement/history` writes the
+//! negotiation-history exhibit, which never includes authority.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::handlers::helpers::matter::db_matter_for_route;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{
+    AuditSeverity, CreateSettlementAuthorityParams, CreateSettlementProposalParams,
+    MatterMemberRole, SettlementAuthorityKind, SettlementAuthorityRecord, SettlementProposalKind,
+    SettlementProposalStatus, SettlementSide,
+};
+use crate::legal::settlement::{
+    check_authority, negotiation_history_path, negotiation_position, render_negotiation_history,
+};
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new()
+        .route(
+            "/api/matters/{id}/settlement",
+            get(matter_settlement_handler),
+        )
+        .route(
+            "/api/matters/{id}/settlement/proposals",
+            post(settlement_proposal_create_handler),
+        )
+        .route(
+            "/api/matters/{id}/settlement/proposals/{proposal_id}/status",
+            post(settlement_proposal_status_handler),
+        )
+        .route(
+            "/api/matters/{id}/settlement/authority",
+            post(settlement_authority_create_handler),
+        )
+        .route(
+            "/api/matters/{id}/settlement/check",
+            post(settlement_authority_check_handler),
+        )
+        .route(
+            "/api/matters/{id}/settlement/history",
+            post(settlement_history_export_handler),
+        )
+}
+
+/// The grant in force, if any.
+async fn current_authority(
+    state: &GatewayState,
+    matter_id: &str,
+) -> Result<Option<SettlementAuthorityRecord>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    Ok(store
+        .list_settlement_authority(&state.user_id, matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .into_iter()
+        .next())
+}
+
+fn parse_proposal_kind(raw: &str) -> Result<SettlementProposalKind, (StatusCode, String)> {
+    SettlementProposalKind::from_db_value(raw.trim().to_ascii_lowercase().as_str()).ok_or((
+        StatusCode::BAD_REQUEST,
+        "'kind' must be 'demand' or 'offer'".to_string(),
+    ))
+}
+
+fn parse_side(raw: &str) -> Result<SettlementSide, (StatusCode, String)> {
+    SettlementSide::from_db_value(raw.trim().to_ascii_lowercase().as_str()).ok_or((
+        StatusCode::BAD_REQUEST,
+        "'side' must be 'client' or 'opposing'".to_string(),
+    ))
+}
+
+fn parse_proposal_status(raw: &str) -> Result<SettlementProposalStatus, (StatusCode, String)> {
+    SettlementProposalStatus::from_db_value(raw.trim().to_ascii_lowercase().as_str()).ok_or((
+        StatusCode::BAD_REQUEST,
+        "'status' must be one of open, accepted, rejected, countered, withdrawn, expired"
+            .to_string(),
+    ))
+}
+
+fn parse_authority_kind(raw: &str) -> Result<SettlementAuthorityKind, (StatusCode, String)> {
+    SettlementAuthorityKind::from_db_value(raw.trim().to_ascii_lowercase().as_str()).ok_or((
+        StatusCode::BAD_REQUEST,
+        "'kind' must be 'minimum' (lowest the client will accept) or 'maximum' (most the client will pay)"
+            .to_string(),
+    ))
+}
+
+pub(crate) async fn matter_settlement_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<MatterSettlementResponse>, (StatusCode, String)> {
+    let matter_id = db_matter_for_route(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Viewer,
+    )
+    .await?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let proposals = store
+        .list_settlement_proposals(&state.user_id, &matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let grants = store
+        .list_settlement_authority(&state.user_id, &matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let matter_prefix =
+        crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), &matter_id);
+    Ok(Json(MatterSettlementResponse {
+        position: negotiation_position(&proposals),
+        proposals: proposals
+            .into_iter()
+            .map(crate::channels::web::server::settlement_proposal_record_to_info)
+            .collect(),
+        authority: grants
+            .first()
+            .cloned()
+            .map(crate::channels::web::server::settlement_authority_record_to_info),
+        authority_history: grants
+            .into_iter()
+            .map(crate::channels::web::server::settlement_authority_record_to_info)
+            .collect(),
+        negotiation_history_path: negotiation_history_path(&matter_prefix),
+        matter_id,
+    }))
+}
+
+pub(crate) async fn settlement_proposal_create_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<CreateSettlementProposalRequest>,
+) -> Result<(StatusCode, Json<SettlementProposalResponse>), (StatusCode, String)> {
+    let matter_id = db_matter_for_route(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let kind = parse_proposal_kind(&req.kind)?;
+    let side = parse_side(&req.side)?;
+    let from_party = req.from_party.trim().to_string();
+    if from_party.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'from_party' is required".to_string(),
+        ));
+    }
+    let to_party = crate::channels::web::server::parse_optional_matter_field(req.to_party);
+    let terms = crate::channels::web::server::parse_optional_matter_field(req.terms);
+    for (field, value) in [
+        ("from_party", &Some(from_party.clone())),
+        ("to_party", &to_party),
+        ("terms", &terms),
+    ] {
+        crate::channels::web::server::validate_optional_matter_field_length(field, value)?;
+    }
+    let amount = crate::channels::web::server::parse_optional_decimal_field("amount", req.amount)?
+        .map(|amount| amount.round_dp(2));
+    if amount.is_none() && terms.is_none() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'amount' or 'terms' is required".to_string(),
+        ));
+    }
+    let proposed_on = match req.proposed_on.as_deref() {
+        Some(raw) if !raw.trim().is_empty() => {
+            crate::channels::web::server::parse_date_only("proposed_on", raw)?
+        }
+        _ => Utc::now().date_naive(),
+    };
+    let expires_on = req
+        .expires_on
+        .as_deref()
+        .filter(|raw| !raw.trim().is_empty())
+        .map(|raw| crate::channels::web::server::parse_date_only("expires_on", raw))
+        .transpose()?;
+    if expires_on.is_some_and(|expires_on| expires_on < proposed_on) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'expires_on' must not be before 'proposed_on'".to_string(),
+        ));
+    }
+
+    let proposal = store
+        .create_settlement_proposal(
+            &state.user_id,
+            &matter_id,
+            &CreateSettlementProposalParams {
+                kind,
+                side,
+                from_party,
+                to_party,
+                amount,
+                terms,
+                proposed_on,
+                expires_on,
+                created_by: principal.user_id.clone(),
+            },
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let authority_check = match (side, proposal.amount) {
+        (SettlementSide::Client, Some(amount)) => current_authority(state.as_ref(), &matter_id)
+            .await?
+            .map(|authority| check_authority(&authority, amount)),
+        _ => None,
+    };
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "settlement_proposal_recorded",
+        &principal.user_id,
+        Some(matter_id.as_str()),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "proposal_id": proposal.id.to_string(),
+            "kind": proposal.kind.as_str(),
+            "side": proposal.side.as_str(),
+            "amount": proposal.amount.map(|amount| amount.to_string()),
+            "proposed_on": proposal.proposed_on.to_string(),
+        }),
+    )
+    .await;
+    if let Some(check) = authority_check.as_ref().filter(|c| !c.within_authority) {
+        crate::channels::web::server::record_legal_audit_event(
+            state.as_ref(),
+            "settlement_authority_exceeded",
+            &principal.user_id,
+            Some(matter_id.as_str()),
+            AuditSeverity::Warn,
+            serde_json::json!({
+                "proposal_id": proposal.id.to_string(),
+                "authority_id": check.authority_id,
+                "amount": check.amount.to_string(),
+                "limit": check.limit.to_string(),
+            }),
+        )
+        .await;
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SettlementProposalResponse {
+            proposal: crate::channels::web::server::settlement_proposal_record_to_info(proposal),
+            authority_check,
+        }),
+    ))
+}
+
+pub(crate) async fn settlement_proposal_status_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path((id, proposal_id)): Path<(String, String)>,
+    Json(req): Json<UpdateSettlementProposalStatusRequest>,
+) -> Result<Json<SettlementProposalInfo>, (StatusCode, String)> {
+    let matter_id = db_matter_for_route(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let proposal_id = Uuid::parse_str(&proposal_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid proposal id".to_string()))?;
+    let status = parse_proposal_status(&req.status)?;
+    let response_notes =
+        crate::channels::web::server::parse_optional_matter_field(req.response_notes);
+    crate::channels::web::server::validate_optional_matter_field_length(
+        "response_notes",
+        &response_notes,
+    )?;
+    let proposal = store
+        .update_settlement_proposal_status(
+            &state.user_id,
+            &matter_id,
+            proposal_id,
+            status,
+            response_notes.as_deref(),
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Proposal not found".to_string()))?;
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "settlement_proposal_status_changed",
+        &principal.user_id,
+        Some(matter_id.as_str()),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "proposal_id": proposal.id.to_string(),
+            "status": proposal.status.as_str(),
+        }),
+    )
+    .await;
+
+    Ok(Json(
+        crate::channels::web::server::settlement_proposal_record_to_info(proposal),
+    ))
+}
+
+pub(crate) async fn settlement_authority_create_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<CreateSettlementAuthorityRequest>,
+) -> Result<(StatusCode, Json<SettlementAuthorityInfo>), (StatusCode, String)> {
+    let matter_id = db_matter_for_route(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Owner,
+    )
+    .await?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let kind = parse_authority_kind(&req.kind)?;
+    let amount =
+        crate::channels::web::server::parse_decimal_field("amount", &req.amount)?.round_dp(2);
+    let granted_on = match req.granted_on.as_deref() {
+        Some(raw) if !raw.trim().is_empty() => {
+            crate::channels::web::server::parse_date_only("granted_on", raw)?
+        }
+        _ => Utc::now().date_naive(),
+    };
+    let granted_by = crate::channels::web::server::parse_optional_matter_field(req.granted_by);
+    let notes = crate::channels::web::server::parse_optional_matter_field(req.notes);
+    crate::channels::web::server::validate_optional_matter_field_length("granted_by", &granted_by)?;
+    crate::channels::web::server::validate_optional_matter_field_length("notes", &notes)?;
+
+    let authority = store
+        .create_settlement_authority(
+            &state.user_id,
+            &matter_id,
+            &CreateSettlementAuthorityParams {
+                kind,
+                amount,
+                granted_on,
+                granted_by,
+                notes,
+                created_by: principal.user_id.clone(),
+            },
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "settlement_authority_recorded",
+        &principal.user_id,
+        Some(matter_id.as_str()),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "authority_id": authority.id.to_string(),
+            "kind": authority.kind.as_str(),
+            "granted_on": authority.granted_on.to_string(),
+        }),
+    )
+    .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(crate::channels::web::server::settlement_authority_record_to_info(authority)),
+    ))
+}
+
+pub(crate) async fn settlement_authority_check_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<SettlementAuthorityCheckRequest>,
+) -> Result<Json<SettlementAuthorityCheckResponse>, (StatusCode, String)> {
+    let matter_id = db_matter_for_route(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Viewer,
+    )
+    .await?;
+    let amount = crate::channels::web::server::parse_decimal_field("amount", &req.amount)?;
+    let check = current_authority(state.as_ref(), &matter_id)
+        .await?
+        .map(|authority| check_authority(&authority, amount));
+    Ok(Json(SettlementAuthorityCheckResponse { matter_id, check }))
+}
+
+pub(crate) async fn settlement_history_export_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<(StatusCode, Json<SettlementHistoryExportResponse>), (StatusCode, String)> {
+    let matter_id = db_matter_for_route(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let proposals = store
+        .list_settlement_proposals(&state.user_id, &matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let (caption, _) = crate::channels::web::handlers::matters::exhibits::matter_caption_block(
+        state.as_ref(),
+        &matter_id,
+        "Settlement Negotiation History",
+    )
+    .await?;
+
+    let generated_at = Utc::now();
+    let matter_prefix =
+        crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), &matter_id);
+    let path = negotiation_history_path(&matter_prefix);
+    workspace
+        .write(
+            &path,
+            &render_negotiation_history(&matter_id, caption.as_deref(), &proposals, generated_at),
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SettlementHistoryExportResponse {
+            matter_id,
+            path,
+            generated_at: generated_at.to_rfc3339(),
+            proposal_count: proposals.len(),
+        }),
+    ))
+}
@@ -0,0 +1,433 @@
+//! Client status report approval handlers.
+//!
+//! Drafts queued by the `client_status_report` tool are reviewed here. An
+//! attorney's approval is the only path by which a report reaches the
+//! email tool, and only once the client's communication preferences clear
+//! it; their disclaimers are appended to the body.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::handlers::helpers::matter::require_matter_access;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::context::JobContext;
+use crate::db::{
+    AuditSeverity, ClientStatusReportRecord, ClientStatusReportStatus, CommunicationChannel,
+    MatterMemberRole, UpdateClientStatusReportParams,
+};
+use crate::legal::client_comms::{SendClearance, clear_client_send};
+use crate::legal::status_report::{EMAIL_TOOL_NAME, email_params};
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new()
+        .route(
+            "/api/matters/{id}/status-reports",
+            get(matter_status_reports_handler),
+        )
+        .route(
+            "/api/matters/{id}/status-reports/{report_id}",
+            get(matter_status_report_detail_handler),
+        )
+        .route(
+            "/api/matters/{id}/status-reports/{report_id}/approve",
+            post(matter_status_report_approve_handler),
+        )
+        .route(
+            "/api/matters/{id}/status-reports/{report_id}/reject",
+            post(matter_status_report_reject_handler),
+        )
+}
+
+async fn load_status_report(
+    state: &GatewayState,
+    principal_user_id: &str,
+    raw_matter_id: &str,
+    raw_report_id: &str,
+    role: MatterMemberRole,
+) -> Result<(String, ClientStatusReportRecord), (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(raw_matter_id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        principal_user_id,
+        role,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    let report_id = crate::channels::web::server::parse_uuid(raw_report_id, "report_id")?;
+    let report = store
+        .get_client_status_report(&state.user_id, &matter_id, report_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Status report not found".to_string()))?;
+    Ok((matter_id, report))
+}
+
+async fn update_status_report(
+    state: &GatewayState,
+    report: &ClientStatusReportRecord,
+    input: UpdateClientStatusReportParams,
+) -> Result<ClientStatusReportRecord, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    store
+        .update_client_status_report(&state.user_id, &report.matter_id, report.id, &input)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Status report not found".to_string()))
+}
+
+/// Hand an approved report to the email tool.
+async fn deliver_status_report(
+    state: &GatewayState,
+    report: &ClientStatusReportRecord,
+    recipient: &str,
+    body: &str,
+) -> Result<(), String> {
+    let tool = match state.tool_registry.as_ref() {
+        Some(registry) => registry.get(EMAIL_TOOL_NAME).await,
+        None => None,
+    }
+    .ok_or_else(|| format!("email tool '{EMAIL_TOOL_NAME}' is not installed"))?;
+    let mut ctx = JobContext::with_user(
+        state.user_id.clone(),
+        "Send client status report",
+        report.subject.clone(),
+    );
+    ctx.metadata = serde_json::json!({ "matter_id": report.matter_id });
+    tool.execute(email_params(recipient, &report.subject, body), &ctx)
+        .await
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+/// Clear a report email against the client's communication preferences.
+/// A refusal is audited and leaves the report pending.
+async fn clear_status_report_send(
+    state: &GatewayState,
+    matter_id: &str,
+    attorney: &str,
+) -> Result<SendClearance, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let Some(matter) = store
+        .get_matter_db(&state.user_id, matter_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+    else {
+        return Ok(SendClearance::default());
+    };
+    match clear_client_send(
+        store.as_ref(),
+        &state.user_id,
+        matter.client_id,
+        CommunicationChannel::Email,
+        chrono::Utc::now(),
+        false,
+    )
+    .await
+    {
+        Ok(clearance) => Ok(clearance),
+        Err(block) => {
+            crate::channels::web::server::record_legal_audit_event(
+                state,
+                "client_message_blocked",
+                attorney,
+                Some(matter_id),
+                AuditSeverity::Warn,
+                serde_json::json!({
+                    "client_id": matter.client_id.to_string(),
+                    "channel": CommunicationChannel::Email.as_str(),
+                    "reason": block.reason,
+                    "retry_at": block.retry_at.map(|at| at.to_rfc3339()),
+                }),
+            )
+            .await;
+            Err((
+                StatusCode::CONFLICT,
+                format!(
+                    "Client communication preferences hold this email: {}",
+                    block.reason
+                ),
+            ))
+        }
+    }
+}
+
+pub(crate) async fn matter_status_reports_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<ClientStatusReportsResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(&id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Viewer,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    let reports = store
+        .list_client_status_reports(&state.user_id, &matter_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    Ok(Json(ClientStatusReportsResponse {
+        reports: reports
+            .into_iter()
+            .map(crate::channels::web::server::client_status_report_record_to_info)
+            .collect(),
+    }))
+}
+
+pub(crate) async fn matter_status_report_detail_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path((id, report_id)): Path<(String, String)>,
+) -> Result<Json<ClientStatusReportDetailResponse>, (StatusCode, String)> {
+    let (_, report) = load_status_report(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        &report_id,
+        MatterMemberRole::Viewer,
+    )
+    .await?;
+    let content = match state.workspace.as_ref() {
+        Some(workspace) => workspace
+            .read(&report.report_path)
+            .await
+            .ok()
+            .map(|doc| doc.content),
+        None => None,
+    };
+    Ok(Json(ClientStatusReportDetailResponse {
+        report: crate::channels::web::server::client_status_report_record_to_info(report),
+        content,
+    }))
+}
+
+pub(crate) async fn matter_status_report_approve_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path((id, report_id)): Path<(String, String)>,
+    Json(req): Json<ApproveClientStatusReportRequest>,
+) -> Result<Json<ClientStatusReportResponse>, (StatusCode, String)> {
+    let (matter_id, report) = load_status_report(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        &report_id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    // `Approved` means a previous delivery attempt failed; approving again retries it.
+    if !matches!(
+        report.status,
+        ClientStatusReportStatus::PendingApproval | ClientStatusReportStatus::Approved
+    ) {
+        return Err((
+            StatusCode::CONFLICT,
+            format!("Status report is already {}", report.status.as_str()),
+        ));
+    }
+    let attorney =
+        crate::channels::web::server::parse_required_matter_field("attorney", &req.attorney)?;
+    let recipient = match req.recipient.as_deref().map(str::trim) {
+        Some(raw) if !raw.is_empty() => {
+            if !raw.contains('@') {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    "recipient must be an email address".to_string(),
+                ));
+            }
+            raw.to_string()
+        }
+        _ => report.recipient.clone().ok_or((
+            StatusCode::BAD_REQUEST,
+            "No client email on file; provide a recipient".to_string(),
+        ))?,
+    };
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let body = workspace
+        .read(&report.report_path)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::CONFLICT,
+                format!("Report draft '{}' is missing", report.report_path),
+            )
+        })?
+        .content;
+    let body = clear_status_report_send(state.as_ref(), &matter_id, attorney.as_str())
+        .await?
+        .apply(&body);
+
+    let approved = update_status_report(
+        state.as_ref(),
+        &report,
+        UpdateClientStatusReportParams {
+            status: ClientStatusReportStatus::Approved,
+            reviewed_by: Some(attorney.clone()),
+            review_note: req.note.clone(),
+            recipient: Some(recipient.clone()),
+            delivery_error: None,
+        },
+    )
+    .await?;
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "client_status_report_approved",
+        attorney.as_str(),
+        Some(matter_id.as_str()),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "report_id": approved.id.to_string(),
+            "report_path": approved.report_path,
+        }),
+    )
+    .await;
+
+    match deliver_status_report(state.as_ref(), &approved, &recipient, &body).await {
+        Ok(()) => {
+            let sent = update_status_report(
+                state.as_ref(),
+                &approved,
+                UpdateClientStatusReportParams {
+                    status: ClientStatusReportStatus::Sent,
+                    reviewed_by: None,
+                    review_note: None,
+                    recipient: None,
+                    delivery_error: None,
+                },
+            )
+            .await?;
+            crate::channels::web::server::record_legal_audit_event(
+                state.as_ref(),
+                "client_status_report_sent",
+                attorney.as_str(),
+                Some(matter_id.as_str()),
+                AuditSeverity::Info,
+                serde_json::json!({
+                    "report_id": sent.id.to_string(),
+                    "email_tool": EMAIL_TOOL_NAME,
+                }),
+            )
+            .await;
+            Ok(Json(ClientStatusReportResponse {
+                report: crate::channels::web::server::client_status_report_record_to_info(sent),
+            }))
+        }
+        Err(err) => {
+            update_status_report(
+                state.as_ref(),
+                &approved,
+                UpdateClientStatusReportParams {
+                    status: ClientStatusReportStatus::Approved,
+                    reviewed_by: None,
+                    review_note: None,
+                    recipient: None,
+                    delivery_error: Some(err.clone()),
+                },
+            )
+            .await?;
+            crate::channels::web::server::record_legal_audit_event(
+                state.as_ref(),
+                "client_status_report_delivery_failed",
+                attorney.as_str(),
+                Some(matter_id.as_str()),
+                AuditSeverity::Warn,
+                serde_json::json!({
+                    "report_id": approved.id.to_string(),
+                    "email_tool": EMAIL_TOOL_NAME,
+                    "error": err,
+                }),
+            )
+            .await;
+            Err((
+                StatusCode::BAD_GATEWAY,
+                format!("Report approved but delivery failed: {err}"),
+            ))
+        }
+    }
+}
+
+pub(crate) async fn matter_status_report_reject_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path((id, report_id)): Path<(String, String)>,
+    Json(req): Json<RejectClientStatusReportRequest>,
+) -> Result<Json<ClientStatusReportResponse>, (StatusCode, String)> {
+    let (matter_id, report) = load_status_report(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        &report_id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    if !matches!(
+        report.status,
+        ClientStatusReportStatus::PendingApproval | ClientStatusReportStatus::Approved
+    ) {
+        return Err((
+            StatusCode::CONFLICT,
+            format!("Status report is already {}", report.status.as_str()),
+        ));
+    }
+    let attorney =
+        crate::channels::web::server::parse_required_matter_field("attorney", &req.attorney)?;
+    let note = crate::channels::web::server::parse_required_matter_field("note", &req.note)?;
+    let rejected = update_status_report(
+        state.as_ref(),
+        &report,
+        UpdateClientStatusReportParams {
+            status: ClientStatusReportStatus::Rejected,
+            reviewed_by: Some(attorney.clone()),
+            review_note: Some(note),
+            recipient: None,
+            delivery_error: None,
+        },
+    )
+    .await?;
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "client_status_report_rejected",
+        attorney.as_str(),
+        Some(matter_id.as_str()),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "report_id": rejected.id.to_string(),
+            "report_path": rejected.report_path,
+        }),
+    )
+    .await;
+    Ok(Json(ClientStatusReportResponse {
+        report: crate::channels::web::server::client_status_report_record_to_info(rejected),
+    }))
+}
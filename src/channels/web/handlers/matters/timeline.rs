@@ -0,0 +1,326 @@
+//! Matter timeline handlers.
+//!
+//! `GET /api/matters/{id}/timeline` merges chronology facts, docket calendar
+//! rows, calendared deadlines, contact log entries, and document filing
+//! dates into one date-ordered list (see [`crate::legal::timeline`]).
+//! `POST /api/matters/{id}/timeline/export` writes the same list as a
+//! markdown table under `exports/` for mediation presentations.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+use chrono::{NaiveDate, Utc};
+
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::handlers::helpers::matter::matter_for_route;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{MatterFactQuery, MatterMemberRole};
+use crate::legal::timeline::{
+    TimelineEvent, TimelineEventType, parse_event_types, render_timeline_markdown, sort_timeline,
+    timeline_export_path,
+};
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new()
+        .route("/api/matters/{id}/timeline", get(matter_timeline_handler))
+        .route(
+            "/api/matters/{id}/timeline/export",
+            post(matter_timeline_export_handler),
+        )
+}
+
+/// Parsed `from`/`to`/`types` filters.
+struct TimelineFilter {
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    types: Vec<TimelineEventType>,
+}
+
+impl TimelineFilter {
+    fn from_query(query: &MatterTimelineQuery) -> Result<Self, (StatusCode, String)> {
+        let from = query
+            .from
+            .as_deref()
+            .map(|value| crate::channels::web::server::parse_date_only("from", value))
+            .transpose()?;
+        let to = query
+            .to
+            .as_deref()
+            .map(|value| crate::channels::web::server::parse_date_only("to", value))
+            .transpose()?;
+        if let (Some(from), Some(to)) = (from, to)
+            && from > to
+        {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "'from' must not be after 'to'".to_string(),
+            ));
+        }
+        let types =
+            parse_event_types(query.types.as_deref()).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        Ok(Self { from, to, types })
+    }
+
+    fn wants(&self, event_type: TimelineEventType) -> bool {
+        self.types.contains(&event_type)
+    }
+
+    fn in_range(&self, date: NaiveDate) -> bool {
+        self.from.is_none_or(|from| date >= from) && self.to.is_none_or(|to| date <= to)
+    }
+}
+
+/// Collect the matter's timeline. Facts, deadlines, and documents come from
+/// the database and are skipped without one; the docket and contact log are
+/// read from the workspace.
+async fn collect_matter_timeline(
+    state: &GatewayState,
+    matter_id: &str,
+    filter: &TimelineFilter,
+) -> Result<Vec<TimelineEvent>, (StatusCode, String)> {
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let matter_root = crate::channels::web::server::matter_root_for_gateway(state);
+    let matter_prefix = crate::channels::web::server::matter_prefix_for_gateway(state, matter_id);
+    let calendar_path = format!("{matter_prefix}/deadlines/calendar.md");
+    let contact_log_path = crate::legal::correspondence::contact_log_path(&matter_root, matter_id);
+    let mut events = Vec::new();
+    let mut deadline_keys = Vec::new();
+
+    if let Some(store) = state.store.as_ref() {
+        if filter.wants(TimelineEventType::Fact) {
+            let facts = store
+                .list_matter_facts(
+                    &state.user_id,
+                    matter_id,
+                    &MatterFactQuery {
+                        from: filter.from,
+                        to: filter.to,
+                        source_path: None,
+                        min_confidence: None,
+                    },
+                )
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            events.extend(facts.into_iter().map(|fact| TimelineEvent {
+                date: fact.fact_date,
+                at: None,
+                event_type: TimelineEventType::Fact,
+                title: fact.description,
+                detail: fact.source_excerpt,
+                source: fact.source_path,
+                source_id: Some(fact.id.to_string()),
+                source_locator: fact.source_locator,
+                status: None,
+            }));
+        }
+
+        if filter.wants(TimelineEventType::Deadline) || filter.wants(TimelineEventType::Docket) {
+            let deadlines = store
+                .list_matter_deadlines(&state.user_id, matter_id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            for deadline in deadlines {
+                let date = deadline.due_at.date_naive();
+                deadline_keys.push((date, deadline.title.to_lowercase()));
+                if !filter.wants(TimelineEventType::Deadline) {
+                    continue;
+                }
+                let detail = match &deadline.rule_ref {
+                    Some(rule) => format!("{} ({rule})", deadline.deadline_type.as_str()),
+                    None => deadline.deadline_type.as_str().to_string(),
+                };
+                events.push(TimelineEvent {
+                    date,
+                    at: Some(deadline.due_at),
+                    event_type: TimelineEventType::Deadline,
+                    title: deadline.title,
+                    detail: Some(detail),
+                    source: "matter_deadlines".to_string(),
+                    source_id: Some(deadline.id.to_string()),
+                    source_locator: None,
+                    status: Some(
+                        if deadline.completed_at.is_some() {
+                            "completed"
+                        } else {
+                            "open"
+                        }
+                        .to_string(),
+                    ),
+                });
+            }
+        }
+
+        if filter.wants(TimelineEventType::Document) {
+            crate::channels::web::server::ensure_matter_db_row_from_workspace(state, matter_id)
+                .await?;
+            crate::channels::web::server::backfill_matter_documents_from_workspace(
+                state, matter_id,
+            )
+            .await?;
+            let documents = store
+                .list_matter_documents_db(&state.user_id, matter_id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            let exports_prefix = format!("{matter_prefix}/exports/");
+            let metadata_path = format!("{matter_prefix}/matter.yaml");
+            events.extend(
+                documents
+                    .into_iter()
+                    .filter(|doc| {
+                        !doc.path.starts_with(&exports_prefix)
+                            && doc.path != calendar_path
+                            && doc.path != contact_log_path
+                            && doc.path != metadata_path
+                    })
+                    .map(|doc| TimelineEvent {
+                        date: doc.created_at.date_naive(),
+                        at: Some(doc.created_at),
+                        event_type: TimelineEventType::Document,
+                        title: doc.display_name,
+                        detail: Some(doc.category.as_str().to_string()),
+                        source: doc.path,
+                        source_id: Some(doc.id.to_string()),
+                        source_locator: None,
+                        status: None,
+                    }),
+            );
+        }
+    }
+
+    if filter.wants(TimelineEventType::Docket) {
+        let today = Utc::now().date_naive();
+        let rows = crate::channels::web::server::read_matter_deadlines(
+            workspace.as_ref(),
+            &matter_prefix,
+            today,
+        )
+        .await?;
+        for row in rows {
+            let Ok(date) = NaiveDate::parse_from_str(&row.date, "%Y-%m-%d") else {
+                continue;
+            };
+            // Rows mirrored into the deadline engine are already listed.
+            if deadline_keys.contains(&(date, row.title.to_lowercase())) {
+                continue;
+            }
+            events.push(TimelineEvent {
+                date,
+                at: None,
+                event_type: TimelineEventType::Docket,
+                title: row.title,
+                detail: row.source,
+                source: calendar_path.clone(),
+                source_id: None,
+                source_locator: None,
+                status: row.status,
+            });
+        }
+    }
+
+    if filter.wants(TimelineEventType::Communication)
+        && let Ok(doc) = workspace.read(&contact_log_path).await
+    {
+        events.extend(
+            crate::legal::correspondence::parse_contact_log(&doc.content)
+                .into_iter()
+                .map(|entry| TimelineEvent {
+                    date: entry.date,
+                    at: None,
+                    event_type: TimelineEventType::Communication,
+                    title: if entry.with.is_empty() {
+                        entry.channel
+                    } else {
+                        format!("{} with {}", entry.channel, entry.with)
+                    },
+                    detail: Some(entry.summary).filter(|summary| !summary.is_empty()),
+                    source: contact_log_path.clone(),
+                    source_id: None,
+                    source_locator: None,
+                    status: None,
+                }),
+        );
+    }
+
+    events.retain(|event| filter.in_range(event.date));
+    sort_timeline(&mut events);
+    Ok(events)
+}
+
+pub(crate) async fn matter_timeline_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Query(query): Query<MatterTimelineQuery>,
+) -> Result<Json<MatterTimelineResponse>, (StatusCode, String)> {
+    let matter_id = matter_for_route(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Viewer,
+    )
+    .await?;
+    let filter = TimelineFilter::from_query(&query)?;
+    let events = collect_matter_timeline(state.as_ref(), &matter_id, &filter).await?;
+    Ok(Json(MatterTimelineResponse {
+        matter_id,
+        from: filter.from.map(|date| date.to_string()),
+        to: filter.to.map(|date| date.to_string()),
+        types: filter.types.iter().map(|t| t.as_str()).collect(),
+        events,
+    }))
+}
+
+pub(crate) async fn matter_timeline_export_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Query(query): Query<MatterTimelineQuery>,
+) -> Result<(StatusCode, Json<MatterTimelineExportResponse>), (StatusCode, String)> {
+    let matter_id = matter_for_route(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let filter = TimelineFilter::from_query(&query)?;
+    let events = collect_matter_timeline(state.as_ref(), &matter_id, &filter).await?;
+
+    let generated_at = Utc::now();
+    let matter_prefix =
+        crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), &matter_id);
+    let path = timeline_export_path(
+        &matter_prefix,
+        &generated_at.format("%Y%m%d-%H%M%S").to_string(),
+    );
+    workspace
+        .write(
+            &path,
+            &render_timeline_markdown(&matter_id, &events, generated_at),
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(MatterTimelineExportResponse {
+            matter_id,
+            path,
+            generated_at: generated_at.to_rfc3339(),
+            event_count: events.len(),
+        }),
+    ))
+}
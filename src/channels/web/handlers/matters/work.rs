@@ -1,21 +1,24 @@
-//! Matter workstream handlers (tasks and notes).
+//! Matter workstream handlers (tasks, notes, and chronology).
 
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 
 use axum::{
     Json, Router,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
-    routing::get,
+    routing::{get, post},
 };
+use uuid::Uuid;
 
 use crate::channels::web::auth::RequestPrincipal;
 use crate::channels::web::handlers::helpers::matter::require_matter_access;
 use crate::channels::web::state::GatewayState;
 use crate::channels::web::types::*;
 use crate::db::{
-    CreateMatterNoteParams, CreateMatterTaskParams, MatterMemberRole, MatterTaskStatus,
-    UpdateMatterNoteParams, UpdateMatterTaskParams,
+    AuditSeverity, CreateMatterNoteParams, CreateMatterTaskParams, Database, MatterFactQuery,
+    MatterMemberRole, MatterTaskChecklistItem, MatterTaskMove, MatterTaskRecord, MatterTaskStatus,
+    NewNotificationParams, NotificationKind, UpdateMatterNoteParams, UpdateMatterTaskParams,
 };
 
 pub fn routes() -> Router<Arc<GatewayState>> {
@@ -24,6 +27,18 @@ pub fn routes() -> Router<Arc<GatewayState>> {
             "/api/matters/{id}/tasks",
             get(matter_tasks_list_handler).post(matter_tasks_create_handler),
         )
+        .route(
+            "/api/matters/{id}/tasks/board",
+            get(matter_tasks_board_handler),
+        )
+        .route(
+            "/api/matters/{id}/tasks/board/move",
+            post(matter_tasks_board_move_handler),
+        )
+        .route(
+            "/api/matters/{id}/tasks/board/reorder",
+            post(matter_tasks_board_reorder_handler),
+        )
         .route(
             "/api/matters/{id}/tasks/{task_id}",
             axum::routing::patch(matter_tasks_patch_handler).delete(matter_tasks_delete_handler),
@@ -36,6 +51,10 @@ pub fn routes() -> Router<Arc<GatewayState>> {
             "/api/matters/{id}/notes/{note_id}",
             axum::routing::patch(matter_notes_patch_handler).delete(matter_notes_delete_handler),
         )
+        .route(
+            "/api/matters/{id}/chronology",
+            get(matter_chronology_handler),
+        )
 }
 
 pub(crate) async fn matter_tasks_list_handler(
@@ -98,6 +117,14 @@ pub(crate) async fn matter_tasks_create_handler(
         .unwrap_or(MatterTaskStatus::Todo);
     let due_at = crate::channels::web::server::parse_optional_datetime("due_at", req.due_at)?;
     let blocked_by = crate::channels::web::server::parse_uuid_list(&req.blocked_by, "blocked_by")?;
+    let parent_task_id = crate::channels::web::server::parse_optional_uuid_field(
+        req.parent_task_id,
+        "parent_task_id",
+    )?;
+    if let Some(parent_id) = parent_task_id {
+        validate_task_parent(state.as_ref(), &matter_id, None, parent_id).await?;
+    }
+    let checklist = normalize_task_checklist(req.checklist)?;
     let task = store
         .create_matter_task(
             &state.user_id,
@@ -111,6 +138,8 @@ pub(crate) async fn matter_tasks_create_handler(
                 assignee: crate::channels::web::server::parse_optional_matter_field(req.assignee),
                 due_at,
                 blocked_by,
+                parent_task_id,
+                checklist,
             },
         )
         .await
@@ -157,6 +186,14 @@ pub(crate) async fn matter_tasks_patch_handler(
         .map(|values| crate::channels::web::server::parse_uuid_list(values, "blocked_by"))
         .transpose()?;
     let due_at = crate::channels::web::server::parse_optional_datetime_patch("due_at", req.due_at)?;
+    let parent_task_id = crate::channels::web::server::parse_optional_uuid_patch_field(
+        req.parent_task_id,
+        "parent_task_id",
+    )?;
+    if let Some(Some(parent_id)) = parent_task_id {
+        validate_task_parent(state.as_ref(), &matter_id, Some(task_id), parent_id).await?;
+    }
+    let checklist = req.checklist.map(normalize_task_checklist).transpose()?;
     let task = store
         .update_matter_task(
             &state.user_id,
@@ -177,6 +214,8 @@ pub(crate) async fn matter_tasks_patch_handler(
                 }),
                 due_at,
                 blocked_by,
+                parent_task_id,
+                checklist,
             },
         )
         .await
@@ -218,6 +257,438 @@ pub(crate) async fn matter_tasks_delete_handler(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Trim checklist items and reject blank ones.
+fn normalize_task_checklist(
+    items: Vec<MatterTaskChecklistItem>,
+) -> Result<Vec<MatterTaskChecklistItem>, (StatusCode, String)> {
+    items
+        .into_iter()
+        .map(|item| {
+            let text = item.text.trim();
+            if text.is_empty() {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    "'checklist' items must have text".to_string(),
+                ));
+            }
+            Ok(MatterTaskChecklistItem {
+                text: text.to_string(),
+                done: item.done,
+            })
+        })
+        .collect()
+}
+
+/// Ensure `parent_id` is a task on the matter and, when re-parenting
+/// `task_id`, that the move would not create a cycle.
+async fn validate_task_parent(
+    state: &GatewayState,
+    matter_id: &str,
+    task_id: Option<Uuid>,
+    parent_id: Uuid,
+) -> Result<(), (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let parents: HashMap<Uuid, Option<Uuid>> = store
+        .list_matter_tasks(&state.user_id, matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .into_iter()
+        .map(|task| (task.id, task.parent_task_id))
+        .collect();
+    if !parents.contains_key(&parent_id) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'parent_task_id' must reference a task on this matter".to_string(),
+        ));
+    }
+    let mut seen = HashSet::new();
+    let mut current = Some(parent_id);
+    while let Some(id) = current.filter(|id| seen.insert(*id)) {
+        if Some(id) == task_id {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "'parent_task_id' cannot be the task itself or one of its subtasks".to_string(),
+            ));
+        }
+        current = parents.get(&id).copied().flatten();
+    }
+    Ok(())
+}
+
+/// Status lanes in board order.
+const BOARD_STATUS_LANES: [MatterTaskStatus; 5] = [
+    MatterTaskStatus::Todo,
+    MatterTaskStatus::InProgress,
+    MatterTaskStatus::Blocked,
+    MatterTaskStatus::Done,
+    MatterTaskStatus::Cancelled,
+];
+
+fn parse_task_swimlane(value: Option<&str>) -> Result<&'static str, (StatusCode, String)> {
+    match value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+        None | Some("") | Some("status") => Ok("status"),
+        Some("assignee") => Ok("assignee"),
+        Some(_) => Err((
+            StatusCode::BAD_REQUEST,
+            "'swimlane' must be one of: status, assignee".to_string(),
+        )),
+    }
+}
+
+/// Group tasks into board lanes ordered by `(position, created_at)`, with
+/// dependency state resolved against the rest of the matter's tasks.
+fn build_task_board(
+    matter_id: &str,
+    swimlane: &str,
+    mut tasks: Vec<MatterTaskRecord>,
+) -> MatterTaskBoardResponse {
+    tasks.sort_by_key(|task| (task.position, task.created_at));
+    let statuses: HashMap<_, _> = tasks.iter().map(|t| (t.id, t.status)).collect();
+    let mut blocks: HashMap<_, Vec<String>> = HashMap::new();
+    for task in &tasks {
+        for blocker in &task.blocked_by {
+            blocks
+                .entry(*blocker)
+                .or_default()
+                .push(task.id.to_string());
+        }
+    }
+
+    let card = |task: MatterTaskRecord| {
+        let open_blockers: Vec<String> = task
+            .blocked_by
+            .iter()
+            // Blockers that were deleted from the matter no longer hold the task up.
+            .filter(|id| {
+                statuses.get(*id).is_some_and(|status| {
+                    !matches!(status, MatterTaskStatus::Done | MatterTaskStatus::Cancelled)
+                })
+            })
+            .map(|id| id.to_string())
+            .collect();
+        MatterTaskBoardCard {
+            is_blocked: task.status == MatterTaskStatus::Blocked || !open_blockers.is_empty(),
+            open_blockers,
+            blocks: blocks.get(&task.id).cloned().unwrap_or_default(),
+            task: crate::channels::web::server::matter_task_record_to_info(task),
+        }
+    };
+
+    let lanes = if swimlane == "assignee" {
+        let mut assigned: BTreeMap<String, Vec<MatterTaskBoardCard>> = BTreeMap::new();
+        let mut unassigned = Vec::new();
+        for task in tasks {
+            match task.assignee.clone() {
+                Some(assignee) => assigned.entry(assignee).or_default().push(card(task)),
+                None => unassigned.push(card(task)),
+            }
+        }
+        assigned
+            .into_iter()
+            .map(|(key, tasks)| MatterTaskBoardLane {
+                key: Some(key),
+                tasks,
+            })
+            .chain(std::iter::once(MatterTaskBoardLane {
+                key: None,
+                tasks: unassigned,
+            }))
+            .collect()
+    } else {
+        let mut by_status: HashMap<&str, Vec<MatterTaskBoardCard>> = HashMap::new();
+        for task in tasks {
+            by_status
+                .entry(task.status.as_str())
+                .or_default()
+                .push(card(task));
+        }
+        BOARD_STATUS_LANES
+            .iter()
+            .map(|status| MatterTaskBoardLane {
+                key: Some(status.as_str().to_string()),
+                tasks: by_status.remove(status.as_str()).unwrap_or_default(),
+            })
+            .collect()
+    };
+
+    MatterTaskBoardResponse {
+        matter_id: matter_id.to_string(),
+        swimlane: swimlane.to_string(),
+        lanes,
+    }
+}
+
+async fn load_task_board(
+    state: &GatewayState,
+    matter_id: &str,
+    swimlane: &str,
+) -> Result<MatterTaskBoardResponse, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let tasks = store
+        .list_matter_tasks(&state.user_id, matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(build_task_board(matter_id, swimlane, tasks))
+}
+
+/// Apply board moves, then return the refreshed board.
+async fn apply_task_board_moves(
+    state: &GatewayState,
+    principal_user_id: &str,
+    id: &str,
+    swimlane: Option<&str>,
+    moves: Vec<MatterTaskMove>,
+) -> Result<MatterTaskBoardResponse, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        principal_user_id,
+        MatterMemberRole::Collaborator,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    crate::channels::web::server::ensure_existing_matter_db(state, &matter_id).await?;
+    let swimlane = parse_task_swimlane(swimlane)?;
+    if moves.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "At least one task is required".to_string(),
+        ));
+    }
+    let mut seen = HashSet::new();
+    if let Some(dup) = moves.iter().find(|m| !seen.insert(m.task_id)) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Task '{}' is listed more than once", dup.task_id),
+        ));
+    }
+    if moves.iter().any(|m| m.position < 0) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'position' must be zero or greater".to_string(),
+        ));
+    }
+    let applied = store
+        .move_matter_tasks(&state.user_id, &matter_id, &moves)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !applied {
+        return Err((StatusCode::NOT_FOUND, "Task not found".to_string()));
+    }
+    load_task_board(state, &matter_id, swimlane).await
+}
+
+/// `GET /api/matters/{id}/tasks/board` — tasks grouped into status or
+/// assignee lanes in board order, with blocked indicators.
+pub(crate) async fn matter_tasks_board_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Query(query): Query<MatterTaskBoardQuery>,
+) -> Result<Json<MatterTaskBoardResponse>, (StatusCode, String)> {
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(&id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Viewer,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    crate::channels::web::server::ensure_existing_matter_db(state.as_ref(), &matter_id).await?;
+    let swimlane = parse_task_swimlane(query.swimlane.as_deref())?;
+    Ok(Json(
+        load_task_board(state.as_ref(), &matter_id, swimlane).await?,
+    ))
+}
+
+/// `POST /api/matters/{id}/tasks/board/move` — move cards between lanes
+/// and positions in one transaction.
+pub(crate) async fn matter_tasks_board_move_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<MoveMatterTasksRequest>,
+) -> Result<Json<MatterTaskBoardResponse>, (StatusCode, String)> {
+    let moves = req
+        .moves
+        .into_iter()
+        .map(|m| {
+            Ok(MatterTaskMove {
+                task_id: crate::channels::web::server::parse_uuid(&m.task_id, "task_id")?,
+                status: m
+                    .status
+                    .as_deref()
+                    .map(crate::channels::web::server::parse_matter_task_status)
+                    .transpose()?,
+                assignee: m.assignee.map(|value| {
+                    value.and_then(|inner| {
+                        crate::channels::web::server::parse_optional_matter_field(Some(inner))
+                    })
+                }),
+                position: m.position,
+            })
+        })
+        .collect::<Result<Vec<_>, (StatusCode, String)>>()?;
+    Ok(Json(
+        apply_task_board_moves(
+            state.as_ref(),
+            &principal.user_id,
+            &id,
+            req.swimlane.as_deref(),
+            moves,
+        )
+        .await?,
+    ))
+}
+
+/// `POST /api/matters/{id}/tasks/board/reorder` — renumber the listed tasks
+/// in the given order, keeping their lanes.
+pub(crate) async fn matter_tasks_board_reorder_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<ReorderMatterTasksRequest>,
+) -> Result<Json<MatterTaskBoardResponse>, (StatusCode, String)> {
+    let moves = req
+        .task_ids
+        .iter()
+        .enumerate()
+        .map(|(index, task_id)| {
+            Ok(MatterTaskMove {
+                task_id: crate::channels::web::server::parse_uuid(task_id, "task_ids")?,
+                status: None,
+                assignee: None,
+                position: index as i64,
+            })
+        })
+        .collect::<Result<Vec<_>, (StatusCode, String)>>()?;
+    Ok(Json(
+        apply_task_board_moves(
+            state.as_ref(),
+            &principal.user_id,
+            &id,
+            req.swimlane.as_deref(),
+            moves,
+        )
+        .await?,
+    ))
+}
+
+/// Push a note change to live subscribers of the matter. The event names the
+/// note but carries none of its text, since it reaches every connection.
+fn broadcast_note_event(
+    state: &GatewayState,
+    matter_id: &str,
+    action: &str,
+    note_id: String,
+    actor: &str,
+) {
+    state.sse.broadcast(SseEvent::MatterNote {
+        matter_id: matter_id.to_string(),
+        action: action.to_string(),
+        note_id,
+        actor: actor.to_string(),
+    });
+}
+
+/// The thread a reply to `parent_id` joins. Threads are one level deep, so a
+/// reply to a reply attaches to that reply's root note.
+async fn note_thread_root(
+    state: &GatewayState,
+    matter_id: &str,
+    parent_id: Uuid,
+) -> Result<Uuid, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    store
+        .list_matter_notes(&state.user_id, matter_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .into_iter()
+        .find(|note| note.id == parent_id)
+        .map(|parent| parent.parent_id.unwrap_or(parent.id))
+        .ok_or((
+            StatusCode::BAD_REQUEST,
+            "'parent_id' must reference a note on this matter".to_string(),
+        ))
+}
+
+/// Notify matter participants named by `mentions`, skipping the author.
+/// Each mention lands in the person's inbox and on their delivery channel.
+async fn notify_note_mentions(
+    state: &GatewayState,
+    store: &dyn Database,
+    matter_id: &str,
+    note: &MatterNoteInfo,
+    mentions: &[String],
+    actor: &str,
+) {
+    if mentions.is_empty() {
+        return;
+    }
+    let participants =
+        match crate::legal::notes::matter_participants(store, &state.user_id, matter_id).await {
+            Ok(participants) => participants,
+            Err(e) => {
+                tracing::warn!(matter_id, "Failed to resolve note mentions: {}", e);
+                return;
+            }
+        };
+    let excerpt = crate::legal::notes::note_excerpt(&note.body);
+    for mentioned_user_id in crate::legal::notes::resolve_mentions(mentions, &participants) {
+        if mentioned_user_id == actor {
+            continue;
+        }
+        state.sse.broadcast(SseEvent::MatterNoteMention {
+            matter_id: matter_id.to_string(),
+            note_id: note.id.clone(),
+            mentioned_user_id: mentioned_user_id.clone(),
+            author: note.author.clone(),
+        });
+        crate::notifications::notify_user(
+            store,
+            NewNotificationParams {
+                user_id: mentioned_user_id.clone(),
+                kind: NotificationKind::Mention,
+                severity: AuditSeverity::Info,
+                title: format!("{} mentioned you on {}", note.author, matter_id),
+                body: excerpt.clone(),
+                matter_id: Some(matter_id.to_string()),
+                source: Some(format!("note:{}", note.id)),
+            },
+        )
+        .await;
+        crate::channels::web::server::record_legal_audit_event(
+            state,
+            "matter_note_mention",
+            actor,
+            Some(matter_id),
+            AuditSeverity::Info,
+            serde_json::json!({
+                "note_id": note.id,
+                "mentioned_user_id": mentioned_user_id,
+            }),
+        )
+        .await;
+    }
+}
+
 pub(crate) async fn matter_notes_list_handler(
     State(state): State<Arc<GatewayState>>,
     RequestPrincipal(principal): RequestPrincipal,
@@ -271,6 +742,13 @@ pub(crate) async fn matter_notes_create_handler(
     crate::channels::web::server::ensure_existing_matter_db(state.as_ref(), &matter_id).await?;
     let author = crate::channels::web::server::parse_required_matter_field("author", &req.author)?;
     let body = crate::channels::web::server::parse_required_matter_field("body", &req.body)?;
+    let parent_id = match crate::channels::web::server::parse_optional_uuid_field(
+        req.parent_id,
+        "parent_id",
+    )? {
+        Some(parent_id) => Some(note_thread_root(state.as_ref(), &matter_id, parent_id).await?),
+        None => None,
+    };
     let note = store
         .create_matter_note(
             &state.user_id,
@@ -279,16 +757,29 @@ pub(crate) async fn matter_notes_create_handler(
                 author,
                 body,
                 pinned: req.pinned,
+                parent_id,
             },
         )
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-    Ok((
-        StatusCode::CREATED,
-        Json(crate::channels::web::server::matter_note_record_to_info(
-            note,
-        )),
-    ))
+    let info = crate::channels::web::server::matter_note_record_to_info(note);
+    broadcast_note_event(
+        state.as_ref(),
+        &matter_id,
+        "created",
+        info.id.clone(),
+        &principal.user_id,
+    );
+    notify_note_mentions(
+        state.as_ref(),
+        store.as_ref(),
+        &matter_id,
+        &info,
+        &crate::legal::notes::extract_mentions(&info.body),
+        &principal.user_id,
+    )
+    .await;
+    Ok((StatusCode::CREATED, Json(info)))
 }
 
 pub(crate) async fn matter_notes_patch_handler(
@@ -313,6 +804,17 @@ pub(crate) async fn matter_notes_patch_handler(
     .map_err(|s| (s, String::new()))?;
     crate::channels::web::server::ensure_existing_matter_db(state.as_ref(), &matter_id).await?;
     let note_id = crate::channels::web::server::parse_uuid(&note_id, "note_id")?;
+    let previous_body = if req.body.is_some() {
+        store
+            .list_matter_notes(&state.user_id, &matter_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .into_iter()
+            .find(|note| note.id == note_id)
+            .map(|note| note.body)
+    } else {
+        None
+    };
     let note = store
         .update_matter_note(
             &state.user_id,
@@ -327,9 +829,26 @@ pub(crate) async fn matter_notes_patch_handler(
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
         .ok_or((StatusCode::NOT_FOUND, "Note not found".to_string()))?;
-    Ok(Json(
-        crate::channels::web::server::matter_note_record_to_info(note),
-    ))
+    let info = crate::channels::web::server::matter_note_record_to_info(note);
+    broadcast_note_event(
+        state.as_ref(),
+        &matter_id,
+        "updated",
+        info.id.clone(),
+        &principal.user_id,
+    );
+    if let Some(previous_body) = previous_body {
+        notify_note_mentions(
+            state.as_ref(),
+            store.as_ref(),
+            &matter_id,
+            &info,
+            &crate::legal::notes::new_mentions(&previous_body, &info.body),
+            &principal.user_id,
+        )
+        .await;
+    }
+    Ok(Json(info))
 }
 
 pub(crate) async fn matter_notes_delete_handler(
@@ -360,5 +879,82 @@ pub(crate) async fn matter_notes_delete_handler(
     if !deleted {
         return Err((StatusCode::NOT_FOUND, "Note not found".to_string()));
     }
+    broadcast_note_event(
+        state.as_ref(),
+        &matter_id,
+        "deleted",
+        note_id.to_string(),
+        &principal.user_id,
+    );
     Ok(StatusCode::NO_CONTENT)
 }
+
+pub(crate) async fn matter_chronology_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Query(query): Query<MatterChronologyQuery>,
+) -> Result<Json<MatterChronologyResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(&id)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Viewer,
+    )
+    .await
+    .map_err(|s| (s, String::new()))?;
+    crate::channels::web::server::ensure_existing_matter_db(state.as_ref(), &matter_id).await?;
+    let from = query
+        .from
+        .as_deref()
+        .map(|value| crate::channels::web::server::parse_date_only("from", value))
+        .transpose()?;
+    let to = query
+        .to
+        .as_deref()
+        .map(|value| crate::channels::web::server::parse_date_only("to", value))
+        .transpose()?;
+    if let (Some(from), Some(to)) = (from, to)
+        && from > to
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'from' must not be after 'to'".to_string(),
+        ));
+    }
+    if let Some(min) = query.min_confidence
+        && !(0.0..=1.0).contains(&min)
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'min_confidence' must be between 0 and 1".to_string(),
+        ));
+    }
+    let source_path = query
+        .source
+        .map(|value| value.trim().trim_matches('/').to_string())
+        .filter(|value| !value.is_empty());
+    let facts = store
+        .list_matter_facts(
+            &state.user_id,
+            &matter_id,
+            &MatterFactQuery {
+                from,
+                to,
+                source_path,
+                min_confidence: query.min_confidence,
+            },
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .into_iter()
+        .map(crate::channels::web::server::matter_fact_record_to_info)
+        .collect();
+    Ok(Json(MatterChronologyResponse { matter_id, facts }))
+}
@@ -1,16 +1,25 @@
 //! Memory API handlers.
 
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::sync::Arc;
 
 use axum::{
     Json, Router,
-    extract::{DefaultBodyLimit, Multipart, Query, State},
-    http::StatusCode,
+    extract::{DefaultBodyLimit, Multipart, Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
     routing::{get, post},
 };
 
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::handlers::helpers::matter::require_matter_access;
 use crate::channels::web::state::GatewayState;
 use crate::channels::web::types::*;
+use crate::db::{MatterDocumentCategory, MatterMemberRole};
+use uuid::Uuid;
+
+/// Search over-fetch factor when hits are filtered by tag.
+const TAG_FILTER_OVERFETCH: usize = 5;
 
 pub fn routes() -> Router<Arc<GatewayState>> {
     Router::new()
@@ -18,7 +27,37 @@ pub fn routes() -> Router<Arc<GatewayState>> {
         .route("/api/memory/list", get(memory_list_handler))
         .route("/api/memory/read", get(memory_read_handler))
         .route("/api/memory/write", post(memory_write_handler))
+        .route("/api/memory/delete", post(memory_delete_handler))
+        .route("/api/memory/history", get(memory_history_handler))
+        .route(
+            "/api/memory/history/restore",
+            post(memory_history_restore_handler),
+        )
+        .route("/api/memory/trash", get(memory_trash_list_handler))
+        .route("/api/memory/git", get(memory_git_status_handler))
+        .route("/api/memory/git/sync", post(memory_git_sync_handler))
+        .route("/api/memory/git/import", post(memory_git_import_handler))
+        .route(
+            "/api/memory/trash/{id}",
+            axum::routing::delete(memory_trash_purge_handler),
+        )
+        .route(
+            "/api/memory/trash/{id}/restore",
+            post(memory_trash_restore_handler),
+        )
         .route("/api/memory/search", post(memory_search_handler))
+        .route(
+            "/api/memory/tags",
+            get(memory_tags_handler)
+                .post(memory_set_tags_handler)
+                .delete(memory_delete_tag_handler),
+        )
+        .route("/api/memory/tags/facets", get(memory_tag_facets_handler))
+        .route("/api/memory/chunks/{chunk_id}", get(memory_chunk_handler))
+        .route(
+            "/api/memory/embedding-status",
+            get(memory_embedding_status_handler),
+        )
         .route(
             "/api/memory/upload",
             post(memory_upload_handler).layer(DefaultBodyLimit::max(
@@ -81,8 +120,25 @@ pub(crate) async fn memory_list_handler(
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
+    let tagged = match query.tag.as_deref().filter(|raw| !raw.trim().is_empty()) {
+        Some(raw) => {
+            let prefix = path.trim_matches('/');
+            let prefix = (!prefix.is_empty()).then(|| format!("{prefix}/"));
+            Some(tagged_paths(state.as_ref(), raw, prefix.as_deref()).await?)
+        }
+        None => None,
+    };
+
     let list_entries: Vec<ListEntry> = entries
         .iter()
+        .filter(|e| match &tagged {
+            None => true,
+            Some(paths) if e.is_directory => {
+                let dir = format!("{}/", e.path.trim_end_matches('/'));
+                paths.iter().any(|p| p.starts_with(&dir))
+            }
+            Some(paths) => paths.contains(&e.path),
+        })
         .map(|e| ListEntry {
             name: e.path.rsplit('/').next().unwrap_or(&e.path).to_string(),
             path: e.path.clone(),
@@ -100,54 +156,573 @@ pub(crate) async fn memory_list_handler(
 pub(crate) async fn memory_read_handler(
     State(state): State<Arc<GatewayState>>,
     Query(query): Query<crate::channels::web::server::ReadQuery>,
-) -> Result<Json<MemoryReadResponse>, (StatusCode, String)> {
+) -> Result<([(header::HeaderName, String); 1], Json<MemoryReadResponse>), (StatusCode, String)> {
     let workspace = state.workspace.as_ref().ok_or((
         StatusCode::SERVICE_UNAVAILABLE,
         "Workspace not available".to_string(),
     ))?;
 
-    let doc = workspace
-        .read(&query.path)
+    let (doc, etag) = workspace
+        .read_with_etag(&query.path)
         .await
         .map_err(|e| (StatusCode::NOT_FOUND, e.to_string()))?;
 
-    Ok(Json(MemoryReadResponse {
-        path: query.path,
-        content: doc.content,
-        updated_at: Some(doc.updated_at.to_rfc3339()),
-    }))
+    Ok((
+        [(header::ETAG, format!("\"{etag}\""))],
+        Json(MemoryReadResponse {
+            path: query.path,
+            content: doc.content,
+            updated_at: Some(doc.updated_at.to_rfc3339()),
+            etag,
+        }),
+    ))
 }
 
+/// `POST /api/memory/write`. An `If-Match` header holding the ETag from a
+/// read makes the write conditional: if the document changed since, the
+/// write is rejected with 409 and a preview of both sets of changes.
 pub(crate) async fn memory_write_handler(
     State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    headers: HeaderMap,
     Json(req): Json<MemoryWriteRequest>,
-) -> Result<Json<MemoryWriteResponse>, (StatusCode, String)> {
-    let workspace = state.workspace.as_ref().ok_or((
-        StatusCode::SERVICE_UNAVAILABLE,
-        "Workspace not available".to_string(),
-    ))?;
+) -> Result<Json<MemoryWriteResponse>, Response> {
+    let workspace = state.workspace.as_ref().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Workspace not available".to_string(),
+        )
+            .into_response()
+    })?;
+    let if_match = headers
+        .get(header::IF_MATCH)
+        .map(|value| value.to_str().map(str::to_string))
+        .transpose()
+        .map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                "If-Match header must be ASCII".to_string(),
+            )
+                .into_response()
+        })?;
 
     let resolved_path = crate::channels::web::server::resolve_memory_write_path_for_gateway(
         state.as_ref(),
         &req.path,
     )
-    .await?;
+    .await
+    .map_err(IntoResponse::into_response)?;
 
-    workspace
-        .write(&resolved_path, &req.content)
+    let (_, etag) = match workspace
+        .write_if_match(&resolved_path, &req.content, if_match.as_deref())
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    {
+        Ok(written) => written,
+        Err(crate::error::WorkspaceError::WriteConflict { current_etag }) => {
+            let preview = write_conflict_preview(
+                workspace,
+                &resolved_path,
+                if_match.as_deref().unwrap_or_default(),
+                current_etag,
+                &req.content,
+            )
+            .await;
+            return Err((StatusCode::CONFLICT, Json(preview)).into_response());
+        }
+        Err(e) => {
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response());
+        }
+    };
 
     if crate::legal::matter::is_workspace_conflicts_path(&resolved_path) {
         crate::legal::matter::invalidate_conflict_cache();
     }
+    record_mirror_change(state.as_ref(), &principal.user_id, &resolved_path);
+
+    let quota_warning = match crate::channels::web::server::legal_config_for_gateway(state.as_ref())
+    {
+        Ok(legal) => {
+            crate::legal::storage::quota_warning_for_path(workspace, &legal, &resolved_path).await
+        }
+        Err(_) => None,
+    };
 
     Ok(Json(MemoryWriteResponse {
         path: resolved_path,
         status: "written",
+        etag,
+        quota_warning,
+    }))
+}
+
+/// Merge preview for a rejected conditional write: the current content,
+/// what changed since the writer's version, and what the write would change.
+async fn write_conflict_preview(
+    workspace: &crate::workspace::Workspace,
+    path: &str,
+    if_match: &str,
+    current_etag: Option<String>,
+    proposed: &str,
+) -> MemoryWriteConflictResponse {
+    let current_content = match current_etag {
+        Some(_) => workspace.read(path).await.ok().map(|doc| doc.content),
+        None => None,
+    };
+    let base_hash = if_match.trim().trim_start_matches("W/").trim_matches('"');
+    let changes_since_read = match current_content.as_deref() {
+        Some(current) => workspace.history(path).await.ok().and_then(|revisions| {
+            revisions
+                .into_iter()
+                .find(|revision| revision.content_hash == base_hash)
+                .map(|base| crate::workspace::history::line_diff(&base.content, current))
+        }),
+        None => None,
+    };
+    MemoryWriteConflictResponse {
+        error: "Document changed since it was read".to_string(),
+        path: path.to_string(),
+        proposed_changes: crate::workspace::history::line_diff(
+            current_content.as_deref().unwrap_or_default(),
+            proposed,
+        ),
+        current_etag,
+        current_content,
+        changes_since_read,
+    }
+}
+
+/// Attribute a change to `path` to `user_id` in the next git mirror commit.
+fn record_mirror_change(state: &GatewayState, user_id: &str, path: &str) {
+    if let Some(mirror) = state.git_mirror.as_ref() {
+        mirror.record_user_change(user_id, path);
+    }
+}
+
+/// Matter owning `path`, if any.
+pub(crate) fn matter_for_path(state: &GatewayState, path: &str) -> Option<String> {
+    let matter_root = crate::channels::web::server::matter_root_for_gateway(state);
+    crate::legal::workspace_crypto::matter_id_for_path(path, &matter_root)
+}
+
+fn parse_trash_id(raw: &str) -> Result<Uuid, (StatusCode, String)> {
+    Uuid::parse_str(raw).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            "Invalid trash entry ID".to_string(),
+        )
+    })
+}
+
+/// Load a trash entry and check the principal may act on its path.
+async fn trash_entry_for_principal(
+    state: &GatewayState,
+    workspace: &crate::workspace::Workspace,
+    principal_user_id: &str,
+    id: Uuid,
+) -> Result<crate::workspace::TrashedDocument, (StatusCode, String)> {
+    let entry = workspace
+        .get_trashed(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Trash entry not found".to_string()))?;
+    require_document_access(
+        state,
+        principal_user_id,
+        &entry.path,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    Ok(entry)
+}
+
+/// `POST /api/memory/delete` — move a document to the trash. It can be
+/// restored until purged or until trash retention expires.
+pub(crate) async fn memory_delete_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Json(req): Json<MemoryDeleteRequest>,
+) -> Result<Json<TrashEntryInfo>, (StatusCode, String)> {
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let path = require_document_access(
+        state.as_ref(),
+        &principal.user_id,
+        &req.path,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let entry = workspace
+        .trash(&path, &principal.user_id)
+        .await
+        .map_err(|e| match e {
+            crate::error::WorkspaceError::DocumentNotFound { .. } => (
+                StatusCode::NOT_FOUND,
+                format!("Document '{path}' not found"),
+            ),
+            other => (StatusCode::INTERNAL_SERVER_ERROR, other.to_string()),
+        })?;
+    if crate::legal::matter::is_workspace_conflicts_path(&path) {
+        crate::legal::matter::invalidate_conflict_cache();
+    }
+    record_mirror_change(state.as_ref(), &principal.user_id, &path);
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "memory_document_trashed",
+        &principal.user_id,
+        matter_for_path(state.as_ref(), &path).as_deref(),
+        crate::db::AuditSeverity::Info,
+        serde_json::json!({
+            "path": entry.path,
+            "trash_id": entry.id.to_string(),
+        }),
+    )
+    .await;
+    Ok(Json(
+        crate::channels::web::server::trashed_document_to_info(entry),
+    ))
+}
+
+/// `GET /api/memory/trash` — deleted documents, most recent first. Members
+/// only see entries from matters they can view.
+pub(crate) async fn memory_trash_list_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+) -> Result<Json<MemoryTrashResponse>, (StatusCode, String)> {
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let trashed = workspace
+        .list_trash()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let mut visible = HashMap::new();
+    let mut entries = Vec::new();
+    for entry in trashed {
+        if principal.user_id != state.user_id {
+            let Some(matter_id) = matter_for_path(state.as_ref(), &entry.path) else {
+                continue;
+            };
+            let allowed = match visible.get(&matter_id) {
+                Some(allowed) => *allowed,
+                None => {
+                    let allowed = require_matter_access(
+                        &state.store,
+                        &state.user_id,
+                        &matter_id,
+                        &principal.user_id,
+                        MatterMemberRole::Viewer,
+                    )
+                    .await
+                    .is_ok();
+                    visible.insert(matter_id, allowed);
+                    allowed
+                }
+            };
+            if !allowed {
+                continue;
+            }
+        }
+        entries.push(crate::channels::web::server::trashed_document_to_info(
+            entry,
+        ));
+    }
+    Ok(Json(MemoryTrashResponse { entries }))
+}
+
+/// `POST /api/memory/trash/{id}/restore` — put a deleted document back at
+/// its original path. Fails with 409 if a new document was written there.
+pub(crate) async fn memory_trash_restore_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<MemoryRestoreResponse>, (StatusCode, String)> {
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let id = parse_trash_id(&id)?;
+    let entry =
+        trash_entry_for_principal(state.as_ref(), workspace, &principal.user_id, id).await?;
+    workspace
+        .restore_from_trash(id)
+        .await
+        .map_err(|e| match e {
+            crate::error::WorkspaceError::AlreadyExists { path } => (
+                StatusCode::CONFLICT,
+                format!("A document already exists at '{path}'"),
+            ),
+            crate::error::WorkspaceError::DocumentNotFound { .. } => {
+                (StatusCode::NOT_FOUND, "Trash entry not found".to_string())
+            }
+            other => (StatusCode::INTERNAL_SERVER_ERROR, other.to_string()),
+        })?;
+    if crate::legal::matter::is_workspace_conflicts_path(&entry.path) {
+        crate::legal::matter::invalidate_conflict_cache();
+    }
+    record_mirror_change(state.as_ref(), &principal.user_id, &entry.path);
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "memory_document_restored",
+        &principal.user_id,
+        matter_for_path(state.as_ref(), &entry.path).as_deref(),
+        crate::db::AuditSeverity::Info,
+        serde_json::json!({
+            "path": entry.path,
+            "trash_id": id.to_string(),
+            "deleted_by": entry.deleted_by,
+        }),
+    )
+    .await;
+    Ok(Json(MemoryRestoreResponse {
+        id: id.to_string(),
+        path: entry.path,
+        status: "restored",
     }))
 }
 
+/// `GET /api/memory/history?path=` — content revisions of a document,
+/// newest first. `revision=N` returns just that revision with its content.
+pub(crate) async fn memory_history_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Query(query): Query<MemoryHistoryQuery>,
+) -> Result<Json<MemoryHistoryResponse>, (StatusCode, String)> {
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let path = require_document_access(
+        state.as_ref(),
+        &principal.user_id,
+        &query.path,
+        MatterMemberRole::Viewer,
+    )
+    .await?;
+    let revisions = workspace.history(&path).await.map_err(|e| match e {
+        crate::error::WorkspaceError::DocumentNotFound { .. } => (
+            StatusCode::NOT_FOUND,
+            format!("Document '{path}' not found"),
+        ),
+        other => (StatusCode::INTERNAL_SERVER_ERROR, other.to_string()),
+    })?;
+    let revisions = match query.revision {
+        Some(wanted) => {
+            let revision = revisions
+                .into_iter()
+                .find(|r| r.revision == wanted)
+                .ok_or((
+                    StatusCode::NOT_FOUND,
+                    format!("Revision {wanted} of '{path}' not found"),
+                ))?;
+            vec![crate::channels::web::server::document_revision_to_info(
+                revision, true,
+            )]
+        }
+        None => revisions
+            .into_iter()
+            .map(|r| crate::channels::web::server::document_revision_to_info(r, false))
+            .collect(),
+    };
+    Ok(Json(MemoryHistoryResponse { path, revisions }))
+}
+
+/// `POST /api/memory/history/restore` — write an earlier revision back as
+/// the current content. The restore is recorded as a new revision.
+pub(crate) async fn memory_history_restore_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Json(req): Json<MemoryRestoreRevisionRequest>,
+) -> Result<Json<MemoryRestoreRevisionResponse>, (StatusCode, String)> {
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let path = require_document_access(
+        state.as_ref(),
+        &principal.user_id,
+        &req.path,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    workspace
+        .restore_revision(&path, req.revision)
+        .await
+        .map_err(|e| match e {
+            crate::error::WorkspaceError::DocumentNotFound { .. } => (
+                StatusCode::NOT_FOUND,
+                format!("Revision {} of '{path}' not found", req.revision),
+            ),
+            other => (StatusCode::INTERNAL_SERVER_ERROR, other.to_string()),
+        })?;
+    if crate::legal::matter::is_workspace_conflicts_path(&path) {
+        crate::legal::matter::invalidate_conflict_cache();
+    }
+    record_mirror_change(state.as_ref(), &principal.user_id, &path);
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "memory_document_revision_restored",
+        &principal.user_id,
+        matter_for_path(state.as_ref(), &path).as_deref(),
+        crate::db::AuditSeverity::Info,
+        serde_json::json!({
+            "path": path,
+            "revision": req.revision,
+        }),
+    )
+    .await;
+    Ok(Json(MemoryRestoreRevisionResponse {
+        path,
+        restored_revision: req.revision,
+        status: "restored",
+    }))
+}
+
+/// `DELETE /api/memory/trash/{id}` — permanently delete a trash entry.
+pub(crate) async fn memory_trash_purge_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let id = parse_trash_id(&id)?;
+    let entry =
+        trash_entry_for_principal(state.as_ref(), workspace, &principal.user_id, id).await?;
+    let purged = workspace
+        .purge_from_trash(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !purged {
+        return Err((StatusCode::NOT_FOUND, "Trash entry not found".to_string()));
+    }
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "memory_document_purged",
+        &principal.user_id,
+        matter_for_path(state.as_ref(), &entry.path).as_deref(),
+        crate::db::AuditSeverity::Warn,
+        serde_json::json!({
+            "path": entry.path,
+            "trash_id": id.to_string(),
+            "deleted_by": entry.deleted_by,
+            "deleted_at": entry.deleted_at.to_rfc3339(),
+        }),
+    )
+    .await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// The git mirror exports the whole workspace and import overwrites it, so
+/// only administrators may drive it.
+fn git_mirror_for_admin<'a>(
+    state: &'a GatewayState,
+    principal: &crate::channels::web::auth::AuthPrincipal,
+) -> Result<&'a Arc<crate::workspace::git_mirror::GitMirror>, (StatusCode, String)> {
+    if principal.role != crate::db::UserRole::Admin {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only administrators can manage the workspace git mirror".to_string(),
+        ));
+    }
+    state.git_mirror.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace git mirror not configured".to_string(),
+    ))
+}
+
+/// `GET /api/memory/git` — mirror repository, branch, and current head.
+pub(crate) async fn memory_git_status_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+) -> Result<Json<MemoryGitStatusResponse>, (StatusCode, String)> {
+    let mirror = git_mirror_for_admin(state.as_ref(), &principal)?;
+    let head = mirror
+        .head()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(MemoryGitStatusResponse {
+        repo_path: mirror.repo_path().display().to_string(),
+        branch: mirror.branch().to_string(),
+        remote: mirror.remote().map(str::to_string),
+        head,
+        pending_user_changes: mirror.pending_user_changes(),
+    }))
+}
+
+/// `POST /api/memory/git/sync` — commit workspace changes now instead of
+/// waiting for the next scheduled sync.
+pub(crate) async fn memory_git_sync_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+) -> Result<Json<MemoryGitSyncResponse>, (StatusCode, String)> {
+    let mirror = git_mirror_for_admin(state.as_ref(), &principal)?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let commits = mirror
+        .sync(workspace)
+        .await
+        .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+    let head = mirror
+        .head()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(MemoryGitSyncResponse { commits, head }))
+}
+
+/// `POST /api/memory/git/import` — write every file at a mirror revision
+/// back into the workspace. Documents absent from that revision are kept.
+pub(crate) async fn memory_git_import_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Json(req): Json<MemoryGitImportRequest>,
+) -> Result<Json<MemoryGitImportResponse>, (StatusCode, String)> {
+    let mirror = git_mirror_for_admin(state.as_ref(), &principal)?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let rev = req
+        .rev
+        .as_deref()
+        .map(str::trim)
+        .filter(|rev| !rev.is_empty())
+        .unwrap_or(mirror.branch())
+        .to_string();
+    if rev.starts_with('-') {
+        return Err((StatusCode::BAD_REQUEST, "Invalid git revision".to_string()));
+    }
+    let imported = mirror
+        .import(workspace, Some(&rev))
+        .await
+        .map_err(|e| match e {
+            crate::workspace::git_mirror::GitMirrorError::Git { .. } => {
+                (StatusCode::BAD_REQUEST, e.to_string())
+            }
+            other => (StatusCode::INTERNAL_SERVER_ERROR, other.to_string()),
+        })?;
+    crate::legal::matter::invalidate_conflict_cache();
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "memory_git_imported",
+        &principal.user_id,
+        None,
+        crate::db::AuditSeverity::Warn,
+        serde_json::json!({
+            "rev": rev,
+            "imported": imported,
+        }),
+    )
+    .await;
+    Ok(Json(MemoryGitImportResponse { rev, imported }))
+}
+
 pub(crate) async fn memory_search_handler(
     State(state): State<Arc<GatewayState>>,
     Json(req): Json<MemorySearchRequest>,
@@ -157,16 +732,48 @@ pub(crate) async fn memory_search_handler(
         "Workspace not available".to_string(),
     ))?;
 
+    let matter_id = req
+        .matter_id
+        .as_deref()
+        .and_then(crate::legal::policy::sanitize_optional_matter_id);
+    if req.matter_id.is_some() && matter_id.is_none() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'matter_id' is empty after sanitization".to_string(),
+        ));
+    }
+
     let limit = req.limit.unwrap_or(10);
+    let prefix = matter_id
+        .as_deref()
+        .map(|id| crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), id));
+    let tagged = if req.tags.is_empty() {
+        None
+    } else {
+        Some(tagged_paths(state.as_ref(), &req.tags.join(","), prefix.as_deref()).await?)
+    };
+    // Tag filtering drops hits after ranking, so over-fetch to fill the page.
+    let fetch = if tagged.is_some() {
+        limit.saturating_mul(TAG_FILTER_OVERFETCH)
+    } else {
+        limit
+    };
+    let mut config = crate::workspace::SearchConfig::default().with_limit(fetch);
+    if let Some(prefix) = prefix {
+        config = config.with_path_prefix(prefix);
+    }
     let results = workspace
-        .search(&req.query, limit)
+        .search_with_config(&req.query, config)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     let hits: Vec<SearchHit> = results
         .iter()
+        .filter(|r| tagged.as_ref().is_none_or(|paths| paths.contains(&r.path)))
+        .take(limit)
         .map(|r| SearchHit {
-            path: r.document_id.to_string(),
+            path: r.path.clone(),
+            chunk_id: r.chunk_id.to_string(),
             content: r.content.clone(),
             score: r.score as f64,
         })
@@ -175,8 +782,418 @@ pub(crate) async fn memory_search_handler(
     Ok(Json(MemorySearchResponse { results: hits }))
 }
 
+/// Documents matching a comma-separated `key[=value]` tag filter.
+async fn tagged_paths(
+    state: &GatewayState,
+    raw: &str,
+    prefix: Option<&str>,
+) -> Result<BTreeSet<String>, (StatusCode, String)> {
+    let filters =
+        crate::workspace::tags::parse_tag_filters(raw).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let records = store
+        .list_tagged_documents(&state.user_id, prefix)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(crate::workspace::tags::paths_matching_tags(
+        &records, &filters,
+    ))
+}
+
+/// Check access to the document at `path`: matter documents need the given
+/// matter role, other workspace documents are the owner's.
+pub(crate) async fn require_document_access(
+    state: &GatewayState,
+    principal_user_id: &str,
+    path: &str,
+    role: MatterMemberRole,
+) -> Result<String, (StatusCode, String)> {
+    let path = path.trim().trim_matches('/');
+    if path.is_empty() || path.split('/').any(|part| part == "..") {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'path' must be a workspace document path".to_string(),
+        ));
+    }
+    let matter_root = crate::channels::web::server::matter_root_for_gateway(state);
+    match crate::legal::workspace_crypto::matter_id_for_path(path, &matter_root) {
+        Some(matter_id) => {
+            require_matter_access(
+                &state.store,
+                &state.user_id,
+                &matter_id,
+                principal_user_id,
+                role,
+            )
+            .await
+            .map_err(|s| (s, String::new()))?;
+        }
+        None if principal_user_id != state.user_id => {
+            return Err((
+                StatusCode::FORBIDDEN,
+                "Insufficient permissions".to_string(),
+            ));
+        }
+        None => {}
+    }
+    Ok(path.to_string())
+}
+
+/// `GET /api/memory/tags?path=` — tags on one document.
+pub(crate) async fn memory_tags_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Query(query): Query<DocumentTagsQuery>,
+) -> Result<Json<DocumentTagsResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let path = require_document_access(
+        state.as_ref(),
+        &principal.user_id,
+        &query.path,
+        MatterMemberRole::Viewer,
+    )
+    .await?;
+    let tags = store
+        .list_document_tags(&state.user_id, &path)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(DocumentTagsResponse {
+        path,
+        tags: tags
+            .into_iter()
+            .map(crate::channels::web::server::document_tag_record_to_info)
+            .collect(),
+    }))
+}
+
+/// `POST /api/memory/tags` — set tags on an existing document. Setting a key
+/// the document already carries replaces its value.
+pub(crate) async fn memory_set_tags_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Json(req): Json<SetDocumentTagsRequest>,
+) -> Result<Json<DocumentTagsResponse>, (StatusCode, String)> {
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let path = require_document_access(
+        state.as_ref(),
+        &principal.user_id,
+        &req.path,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    if req.tags.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'tags' must not be empty".to_string(),
+        ));
+    }
+    let tags = req
+        .tags
+        .iter()
+        .map(|tag| {
+            Ok(crate::db::SetDocumentTagParams {
+                key: crate::workspace::tags::normalize_tag_key(&tag.key)?,
+                value: crate::workspace::tags::normalize_tag_value(tag.value.as_deref())?,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    if !workspace
+        .exists(&path)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    {
+        return Err((
+            StatusCode::NOT_FOUND,
+            format!("Document '{path}' not found"),
+        ));
+    }
+    let tags = store
+        .set_document_tags(&state.user_id, &path, &tags, &principal.user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(DocumentTagsResponse {
+        path,
+        tags: tags
+            .into_iter()
+            .map(crate::channels::web::server::document_tag_record_to_info)
+            .collect(),
+    }))
+}
+
+/// `DELETE /api/memory/tags?path=&key=` — remove one tag.
+pub(crate) async fn memory_delete_tag_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Query(query): Query<DeleteDocumentTagQuery>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let path = require_document_access(
+        state.as_ref(),
+        &principal.user_id,
+        &query.path,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let key = crate::workspace::tags::normalize_tag_key(&query.key)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let removed = store
+        .remove_document_tag(&state.user_id, &path, &key)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !removed {
+        return Err((StatusCode::NOT_FOUND, format!("Tag '{key}' not found")));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /api/memory/tags/facets?prefix=` — document counts per tag key and
+/// value, for building review filters.
+pub(crate) async fn memory_tag_facets_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Query(query): Query<DocumentTagFacetsQuery>,
+) -> Result<Json<DocumentTagFacetsResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let prefix = query
+        .prefix
+        .as_deref()
+        .map(|p| p.trim().trim_start_matches('/'))
+        .filter(|p| !p.is_empty());
+    match prefix {
+        Some(prefix) => {
+            require_document_access(
+                state.as_ref(),
+                &principal.user_id,
+                prefix,
+                MatterMemberRole::Viewer,
+            )
+            .await?;
+        }
+        None if principal.user_id != state.user_id => {
+            return Err((
+                StatusCode::FORBIDDEN,
+                "Insufficient permissions".to_string(),
+            ));
+        }
+        None => {}
+    }
+    let records = store
+        .list_tagged_documents(&state.user_id, prefix)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let mut counts: BTreeMap<(String, Option<String>), usize> = BTreeMap::new();
+    for record in records {
+        *counts.entry((record.key, record.value)).or_insert(0) += 1;
+    }
+    Ok(Json(DocumentTagFacetsResponse {
+        facets: counts
+            .into_iter()
+            .map(|((key, value), documents)| DocumentTagFacet {
+                key,
+                value,
+                documents,
+            })
+            .collect(),
+    }))
+}
+
+/// Resolve a `[[chunk:<id>]]` citation so the UI can open the document and
+/// highlight the passage.
+pub(crate) async fn memory_chunk_handler(
+    State(state): State<Arc<GatewayState>>,
+    Path(chunk_id): Path<String>,
+) -> Result<Json<MemoryChunkResponse>, (StatusCode, String)> {
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let chunk_id = crate::channels::web::server::parse_uuid(&chunk_id, "chunk_id")?;
+
+    let chunk = workspace
+        .get_chunk(chunk_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Chunk not found".to_string()))?;
+    let passage = match workspace.read(&chunk.path).await {
+        Ok(doc) => crate::workspace::locate_chunk(&doc.content, &chunk.content)
+            .map(|range| doc.content[range].to_string()),
+        Err(_) => None,
+    };
+
+    Ok(Json(MemoryChunkResponse {
+        chunk_id: chunk.chunk_id.to_string(),
+        document_id: chunk.document_id.to_string(),
+        path: chunk.path,
+        chunk_index: chunk.chunk_index,
+        content: chunk.content,
+        passage,
+    }))
+}
+
+/// Resolve the chunk anchors cited in an assistant response, dropping any
+/// that do not belong to this workspace.
+pub(crate) async fn resolve_chunk_citations(
+    state: &GatewayState,
+    content: &str,
+) -> Vec<ChunkCitation> {
+    let Some(workspace) = state.workspace.as_ref() else {
+        return Vec::new();
+    };
+    let mut citations = Vec::new();
+    for chunk_id in crate::workspace::extract_chunk_anchors(content) {
+        match workspace.get_chunk(chunk_id).await {
+            Ok(Some(chunk)) => citations.push(ChunkCitation {
+                chunk_id: chunk.chunk_id.to_string(),
+                path: chunk.path,
+                chunk_index: chunk.chunk_index,
+            }),
+            Ok(None) => {
+                tracing::debug!(%chunk_id, "Dropping citation to unknown chunk");
+            }
+            Err(e) => {
+                tracing::warn!(%chunk_id, "Failed to resolve chunk citation: {}", e);
+            }
+        }
+    }
+    citations
+}
+
+pub(crate) async fn memory_embedding_status_handler(
+    State(state): State<Arc<GatewayState>>,
+) -> Result<Json<EmbeddingStatusResponse>, (StatusCode, String)> {
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+
+    let usage = workspace
+        .storage_usage("")
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let backfill = match state.store.as_ref() {
+        Some(store) => {
+            crate::workspace::backfill::EmbeddingBackfillProgress::load(
+                store.as_ref(),
+                &state.user_id,
+            )
+            .await
+        }
+        None => Default::default(),
+    };
+    let pending_chunks = usage.chunk_count.saturating_sub(usage.embedded_chunk_count);
+    let percent_complete = if usage.chunk_count == 0 {
+        100.0
+    } else {
+        let ratio = usage.embedded_chunk_count as f64 / usage.chunk_count as f64;
+        (ratio * 1000.0).round() / 10.0
+    };
+
+    Ok(Json(EmbeddingStatusResponse {
+        enabled: workspace.has_embeddings(),
+        model: workspace.embedding_model().map(str::to_string),
+        total_chunks: usage.chunk_count,
+        embedded_chunks: usage.embedded_chunk_count,
+        pending_chunks,
+        percent_complete,
+        backfill,
+    }))
+}
+
+/// Upload plain-text files into the workspace.
+///
+/// Files land in `uploads/` unless a `matter_id` text field precedes them,
+/// in which case each file is classified, filed under the matter folder for
+/// its category, and registered as a matter document.
+/// File a deposition transcript as a normalized `page:line` document under
+/// the matter's `transcripts/` folder.
+async fn ingest_transcript_upload(
+    state: &GatewayState,
+    user_id: &str,
+    matter_id: &str,
+    safe_name: &str,
+    content: &str,
+    witness_override: Option<&str>,
+) -> Result<UploadedFile, (StatusCode, String)> {
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let mut transcript = crate::legal::transcript::parse_transcript(content).map_err(|e| {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("File '{safe_name}' is not a readable transcript: {e}"),
+        )
+    })?;
+    if let Some(witness) = witness_override {
+        transcript.witness = Some(witness.to_string());
+    }
+    let matter_prefix = crate::channels::web::server::matter_prefix_for_gateway(state, matter_id);
+    let dest_path = crate::legal::transcript::transcript_path(&matter_prefix, safe_name);
+    let normalized = crate::legal::transcript::render_transcript(&transcript, safe_name);
+    workspace
+        .write(&dest_path, &normalized)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    record_mirror_change(state, user_id, &dest_path);
+
+    crate::channels::web::server::register_ingested_matter_document(
+        state,
+        matter_id,
+        &dest_path,
+        safe_name,
+        MatterDocumentCategory::Evidence,
+    )
+    .await?;
+    let party_candidates = crate::channels::web::server::queue_ingested_party_candidates(
+        state, matter_id, &dest_path, content,
+    )
+    .await;
+
+    Ok(UploadedFile {
+        path: dest_path,
+        bytes: normalized.len(),
+        status: "written",
+        category: Some(MatterDocumentCategory::Evidence),
+        suggested_folder: Some(crate::legal::transcript::TRANSCRIPT_FOLDER),
+        classified_by: None,
+        party_candidates: Some(party_candidates),
+        transcript: Some(UploadedTranscriptInfo {
+            citation: transcript
+                .witness
+                .as_deref()
+                .map(|w| format!("{} Dep.", crate::legal::transcript::citation_name(w))),
+            witness: transcript.witness.clone(),
+            first_page: transcript.pages.first().map_or(0, |page| page.number),
+            last_page: transcript.pages.last().map_or(0, |page| page.number),
+            pages: transcript.pages.len(),
+            lines: transcript.line_count(),
+        }),
+    })
+}
+
 pub(crate) async fn memory_upload_handler(
     State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
     mut multipart: Multipart,
 ) -> Result<(StatusCode, Json<MemoryUploadResponse>), (StatusCode, String)> {
     let workspace = state.workspace.as_ref().ok_or((
@@ -185,6 +1202,9 @@ pub(crate) async fn memory_upload_handler(
     ))?;
 
     let mut uploaded: Vec<UploadedFile> = Vec::new();
+    let mut target_matter: Option<String> = None;
+    let mut transcript_mode = false;
+    let mut witness_override: Option<String> = None;
 
     while let Some(field) = multipart.next_field().await.map_err(|e| {
         (
@@ -192,6 +1212,60 @@ pub(crate) async fn memory_upload_handler(
             format!("Multipart read error: {e}"),
         )
     })? {
+        if field.file_name().is_none() && field.name() == Some("matter_id") {
+            let raw = field.text().await.map_err(|e| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Failed to read 'matter_id': {e}"),
+                )
+            })?;
+            let matter_id_guard = crate::channels::web::server::sanitize_matter_id_for_route(&raw)?;
+            require_matter_access(
+                &state.store,
+                &state.user_id,
+                &matter_id_guard,
+                &principal.user_id,
+                MatterMemberRole::Collaborator,
+            )
+            .await
+            .map_err(|s| (s, String::new()))?;
+            let matter_root = crate::channels::web::server::matter_root_for_gateway(state.as_ref());
+            target_matter = Some(
+                crate::channels::web::server::ensure_existing_matter_for_route(
+                    workspace.as_ref(),
+                    &matter_root,
+                    &raw,
+                )
+                .await?,
+            );
+            continue;
+        }
+        if field.file_name().is_none() && matches!(field.name(), Some("mode" | "witness")) {
+            let name = field.name().unwrap_or_default().to_string();
+            let raw = field.text().await.map_err(|e| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Failed to read '{name}': {e}"),
+                )
+            })?;
+            let value = raw.trim();
+            if name == "witness" {
+                witness_override = (!value.is_empty()).then(|| value.to_string());
+            } else {
+                transcript_mode = match value {
+                    "" | "document" => false,
+                    "transcript" => true,
+                    other => {
+                        return Err((
+                            StatusCode::BAD_REQUEST,
+                            format!("Unknown upload mode '{other}' (expected 'transcript')"),
+                        ));
+                    }
+                };
+            }
+            continue;
+        }
+
         let raw_name = field.file_name().unwrap_or("document.txt").to_string();
         let safe_name: String = raw_name
             .rsplit('/')
@@ -205,8 +1279,6 @@ pub(crate) async fn memory_upload_handler(
         } else {
             safe_name.trim().to_string()
         };
-        let dest_path = format!("uploads/{safe_name}");
-
         let data = field.bytes().await.map_err(|e| {
             (
                 StatusCode::BAD_REQUEST,
@@ -235,16 +1307,86 @@ pub(crate) async fn memory_upload_handler(
             )
         })?;
 
+        if transcript_mode {
+            let Some(matter_id) = target_matter.as_deref() else {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    "Transcript uploads require a 'matter_id' field before the files".to_string(),
+                ));
+            };
+            uploaded.push(
+                ingest_transcript_upload(
+                    state.as_ref(),
+                    &principal.user_id,
+                    matter_id,
+                    &safe_name,
+                    &content,
+                    witness_override.as_deref(),
+                )
+                .await?,
+            );
+            continue;
+        }
+
+        let classification = match target_matter.as_deref() {
+            Some(_) => Some(
+                crate::legal::classify::classify_document(
+                    state.llm_provider.as_deref(),
+                    &safe_name,
+                    &content,
+                )
+                .await,
+            ),
+            None => None,
+        };
+        let dest_path = match (target_matter.as_deref(), classification.as_ref()) {
+            (Some(matter_id), Some(classification)) => format!(
+                "{}/{}/{safe_name}",
+                crate::channels::web::server::matter_prefix_for_gateway(state.as_ref(), matter_id),
+                classification.suggested_folder
+            ),
+            _ => format!("uploads/{safe_name}"),
+        };
+
         let byte_count = content.len();
         workspace
             .write(&dest_path, &content)
             .await
             .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        record_mirror_change(state.as_ref(), &principal.user_id, &dest_path);
+
+        let mut party_candidates = None;
+        if let (Some(matter_id), Some(classification)) =
+            (target_matter.as_deref(), classification.as_ref())
+        {
+            crate::channels::web::server::register_ingested_matter_document(
+                state.as_ref(),
+                matter_id,
+                &dest_path,
+                &safe_name,
+                classification.category,
+            )
+            .await?;
+            party_candidates = Some(
+                crate::channels::web::server::queue_ingested_party_candidates(
+                    state.as_ref(),
+                    matter_id,
+                    &dest_path,
+                    &content,
+                )
+                .await,
+            );
+        }
 
         uploaded.push(UploadedFile {
             path: dest_path,
             bytes: byte_count,
             status: "written",
+            category: classification.as_ref().map(|c| c.category),
+            suggested_folder: classification.as_ref().map(|c| c.suggested_folder),
+            classified_by: classification.as_ref().map(|c| c.source),
+            party_candidates,
+            transcript: None,
         });
     }
 
@@ -3,19 +3,29 @@
 pub mod backups;
 pub mod chat;
 pub mod common;
+pub mod dashboard;
+pub mod email;
 pub mod extensions;
 pub mod gateway;
 pub mod helpers;
+pub mod integrations;
 pub mod jobs;
 pub mod legal;
 pub mod logs;
 pub mod matters;
 pub mod memory;
+pub mod notifications;
+pub mod organizations;
 pub mod pairing;
 pub mod projects;
+pub mod review;
 pub mod routes;
 pub mod routines;
+pub mod search;
 pub mod settings;
+pub mod shares;
 pub mod skills;
 pub mod static_files;
+pub mod templates;
 pub mod users;
+pub mod watchlist;
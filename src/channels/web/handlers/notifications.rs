@@ -0,0 +1,292 @@
+//! Notification inbox handlers. Each caller reads and manages their own inbox.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{Database, NotificationFilter, NotificationKind, NotificationRecord};
+use crate::notifications::{DELIVERY_SETTING_KEY, DeliveryPreference};
+
+/// Listing size when the request does not ask for one.
+const DEFAULT_LIST_LIMIT: i64 = 50;
+const MAX_LIST_LIMIT: i64 = 200;
+/// Longest snooze accepted, so a typo cannot bury an alert for years.
+const MAX_SNOOZE_DAYS: i64 = 90;
+const MAX_DELIVERY_FIELD_LEN: usize = 128;
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new()
+        .route("/api/notifications", get(notifications_list_handler))
+        .route(
+            "/api/notifications/delivery",
+            get(notification_delivery_get_handler)
+                .put(notification_delivery_set_handler)
+                .delete(notification_delivery_delete_handler),
+        )
+        .route(
+            "/api/notifications/read-all",
+            post(notifications_read_all_handler),
+        )
+        .route(
+            "/api/notifications/{id}/read",
+            post(notification_read_handler),
+        )
+        .route(
+            "/api/notifications/{id}/ack",
+            post(notification_ack_handler),
+        )
+        .route(
+            "/api/notifications/{id}/snooze",
+            post(notification_snooze_handler),
+        )
+}
+
+pub(crate) fn notification_to_info(record: NotificationRecord) -> NotificationInfo {
+    NotificationInfo {
+        id: record.id.to_string(),
+        kind: record.kind.as_str(),
+        severity: record.severity.as_str(),
+        title: record.title,
+        body: record.body,
+        matter_id: record.matter_id,
+        source: record.source,
+        read: record.read_at.is_some(),
+        acked: record.acked_at.is_some(),
+        snoozed_until: record.snoozed_until.map(|t| t.to_rfc3339()),
+        created_at: record.created_at.to_rfc3339(),
+    }
+}
+
+fn notification_store(state: &GatewayState) -> Result<&Arc<dyn Database>, (StatusCode, String)> {
+    state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))
+}
+
+fn parse_notification_id(raw: &str) -> Result<Uuid, (StatusCode, String)> {
+    Uuid::parse_str(raw).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            "Invalid notification ID".to_string(),
+        )
+    })
+}
+
+fn found(
+    record: Option<NotificationRecord>,
+) -> Result<Json<NotificationInfo>, (StatusCode, String)> {
+    record
+        .map(|record| Json(notification_to_info(record)))
+        .ok_or((StatusCode::NOT_FOUND, "Notification not found".to_string()))
+}
+
+pub(crate) async fn notifications_list_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Query(query): Query<NotificationListQuery>,
+) -> Result<Json<NotificationListResponse>, (StatusCode, String)> {
+    let store = notification_store(&state)?;
+    let filter = match query.status.as_deref().map(str::trim) {
+        None | Some("") => NotificationFilter::Active,
+        Some(raw) => NotificationFilter::parse(raw).ok_or((
+            StatusCode::BAD_REQUEST,
+            format!("Unknown status '{raw}' (expected active, unread, snoozed, acked, or all)"),
+        ))?,
+    };
+    let kind = match query.kind.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(raw) => Some(NotificationKind::from_db_value(raw).ok_or((
+            StatusCode::BAD_REQUEST,
+            format!("Unknown kind '{raw}' (expected routine, deadline, system, or mention)"),
+        ))?),
+    };
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_LIST_LIMIT)
+        .clamp(1, MAX_LIST_LIMIT);
+
+    let notifications = store
+        .list_notifications(&principal.user_id, filter, kind, limit)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let unread = store
+        .count_unread_notifications(&principal.user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(NotificationListResponse {
+        notifications: notifications
+            .into_iter()
+            .map(notification_to_info)
+            .collect(),
+        unread_count: unread.iter().map(|(_, count)| count).sum(),
+        unread_by_kind: unread
+            .into_iter()
+            .map(|(kind, count)| (kind.as_str(), count))
+            .collect(),
+    }))
+}
+
+pub(crate) async fn notifications_read_all_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+) -> Result<Json<NotificationReadAllResponse>, (StatusCode, String)> {
+    let marked_read = notification_store(&state)?
+        .mark_all_notifications_read(&principal.user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(NotificationReadAllResponse { marked_read }))
+}
+
+pub(crate) async fn notification_read_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<NotificationInfo>, (StatusCode, String)> {
+    let record = notification_store(&state)?
+        .mark_notification_read(&principal.user_id, parse_notification_id(&id)?)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    found(record)
+}
+
+pub(crate) async fn notification_ack_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<NotificationInfo>, (StatusCode, String)> {
+    let record = notification_store(&state)?
+        .ack_notification(&principal.user_id, parse_notification_id(&id)?)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    found(record)
+}
+
+pub(crate) async fn notification_snooze_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<NotificationSnoozeRequest>,
+) -> Result<Json<NotificationInfo>, (StatusCode, String)> {
+    let id = parse_notification_id(&id)?;
+    let now = Utc::now();
+    let until = match (req.minutes, req.until.as_deref()) {
+        // Capped just past the limit so the range check below rejects it.
+        (Some(minutes), None) if minutes > 0 => {
+            now + chrono::Duration::minutes(minutes.min(MAX_SNOOZE_DAYS * 24 * 60 + 1))
+        }
+        (None, Some(raw)) => DateTime::parse_from_rfc3339(raw.trim())
+            .map(|t| t.with_timezone(&Utc))
+            .map_err(|_| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    "'until' must be an RFC 3339 timestamp".to_string(),
+                )
+            })?,
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Provide either a positive 'minutes' or an 'until' timestamp".to_string(),
+            ));
+        }
+    };
+    if until <= now || until > now + chrono::Duration::days(MAX_SNOOZE_DAYS) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Snooze must end in the future and within {MAX_SNOOZE_DAYS} days"),
+        ));
+    }
+    let record = notification_store(&state)?
+        .snooze_notification(&principal.user_id, id, until)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    record
+        .map(|record| Json(notification_to_info(record)))
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            "Notification not found or already acknowledged".to_string(),
+        ))
+}
+
+pub(crate) async fn notification_delivery_get_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+) -> Result<Json<NotificationDeliveryInfo>, (StatusCode, String)> {
+    let store = notification_store(&state)?;
+    crate::notifications::delivery_preference(store.as_ref(), &principal.user_id)
+        .await
+        .map(|preference| {
+            Json(NotificationDeliveryInfo {
+                channel: preference.channel,
+                recipient: preference.recipient,
+            })
+        })
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            "No delivery channel set; notifications stay in the inbox".to_string(),
+        ))
+}
+
+pub(crate) async fn notification_delivery_set_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Json(req): Json<NotificationDeliveryInfo>,
+) -> Result<Json<NotificationDeliveryInfo>, (StatusCode, String)> {
+    let channel = req.channel.trim().to_ascii_lowercase();
+    if channel.is_empty()
+        || channel.len() > MAX_DELIVERY_FIELD_LEN
+        || !channel
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'channel' must be a channel name such as 'telegram' or 'slack'".to_string(),
+        ));
+    }
+    let recipient = req
+        .recipient
+        .map(|raw| raw.trim().to_string())
+        .filter(|raw| !raw.is_empty());
+    if recipient
+        .as_ref()
+        .is_some_and(|raw| raw.len() > MAX_DELIVERY_FIELD_LEN)
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("'recipient' must be at most {MAX_DELIVERY_FIELD_LEN} characters"),
+        ));
+    }
+    let preference = DeliveryPreference { channel, recipient };
+    let value = serde_json::to_value(&preference)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    notification_store(&state)?
+        .set_setting(&principal.user_id, DELIVERY_SETTING_KEY, &value)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(NotificationDeliveryInfo {
+        channel: preference.channel,
+        recipient: preference.recipient,
+    }))
+}
+
+pub(crate) async fn notification_delivery_delete_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+) -> Result<StatusCode, (StatusCode, String)> {
+    notification_store(&state)?
+        .delete_setting(&principal.user_id, DELIVERY_SETTING_KEY)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(StatusCode::NO_CONTENT)
+}
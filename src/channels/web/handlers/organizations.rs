@@ -0,0 +1,383 @@
+//! Organization (firm) handlers: membership and shared matters/clients.
+//!
+//! Members of an organization reach matters shared with it through
+//! `check_matter_access`; settings and everything else stay per user.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, put},
+};
+use uuid::Uuid;
+
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::handlers::helpers::legal::matter_prefix_for_gateway;
+use crate::channels::web::handlers::helpers::mappers::client_record_to_info;
+use crate::channels::web::handlers::helpers::matter::require_matter_access;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{Database, MatterMemberRole, OrgRole, UserRole};
+use crate::error::DatabaseError;
+
+const MAX_ORGANIZATION_NAME_LEN: usize = 200;
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new()
+        .route(
+            "/api/orgs",
+            get(organizations_list_handler).post(organization_create_handler),
+        )
+        .route(
+            "/api/orgs/{org_id}/members",
+            get(organization_members_list_handler),
+        )
+        .route(
+            "/api/orgs/{org_id}/members/{member_user_id}",
+            put(organization_member_upsert_handler).delete(organization_member_remove_handler),
+        )
+        .route(
+            "/api/orgs/{org_id}/matters",
+            get(organization_matters_list_handler),
+        )
+        .route(
+            "/api/orgs/{org_id}/clients",
+            get(organization_clients_list_handler),
+        )
+        .route(
+            "/api/matters/{id}/organization",
+            put(matter_organization_set_handler),
+        )
+        .route(
+            "/api/clients/{id}/organization",
+            put(client_organization_set_handler),
+        )
+}
+
+fn require_store(state: &GatewayState) -> Result<&Arc<dyn Database>, StatusCode> {
+    state.store.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)
+}
+
+fn parse_org_id(raw: &str) -> Result<Uuid, StatusCode> {
+    Uuid::parse_str(raw.trim()).map_err(|_| StatusCode::BAD_REQUEST)
+}
+
+/// The principal's role in `org_id`; 403 when they are not a member.
+async fn require_org_role(
+    store: &Arc<dyn Database>,
+    org_id: Uuid,
+    user_id: &str,
+    manage: bool,
+) -> Result<OrgRole, StatusCode> {
+    let role = store
+        .get_organization_role(org_id, user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("get_organization_role failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::FORBIDDEN)?;
+    if manage && !role.can_manage() {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(role)
+}
+
+/// Map a membership write error; losing the last owner is a 409.
+fn member_write_err(op: &str, err: DatabaseError) -> StatusCode {
+    match err {
+        DatabaseError::Constraint(_) => StatusCode::CONFLICT,
+        err => {
+            tracing::error!("{} failed: {}", op, err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+fn member_response(record: crate::db::OrganizationMemberRecord) -> OrganizationMemberResponse {
+    OrganizationMemberResponse {
+        user_id: record.user_id,
+        role: record.role.as_str().to_string(),
+        created_at: record.created_at.to_rfc3339(),
+        updated_at: record.updated_at.to_rfc3339(),
+    }
+}
+
+/// `GET /api/orgs` — organizations the caller belongs to.
+async fn organizations_list_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+) -> Result<Json<OrganizationsListResponse>, StatusCode> {
+    let store = require_store(&state)?;
+    let rows = store
+        .list_organizations_for_user(&principal.user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("list_organizations_for_user failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    let organizations = rows
+        .into_iter()
+        .map(|(org, role)| OrganizationResponse {
+            id: org.id.to_string(),
+            name: org.name,
+            created_by: org.created_by,
+            role: role.as_str().to_string(),
+            created_at: org.created_at.to_rfc3339(),
+        })
+        .collect();
+    Ok(Json(OrganizationsListResponse { organizations }))
+}
+
+/// `POST /api/orgs` — create an organization owned by the caller
+/// (Admin or Attorney only).
+async fn organization_create_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Json(body): Json<CreateOrganizationRequest>,
+) -> Result<(StatusCode, Json<OrganizationResponse>), StatusCode> {
+    if !matches!(principal.role, UserRole::Admin | UserRole::Attorney) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let name = body.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_ORGANIZATION_NAME_LEN {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let store = require_store(&state)?;
+    let org = store
+        .create_organization(name, &principal.user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("create_organization failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok((
+        StatusCode::CREATED,
+        Json(OrganizationResponse {
+            id: org.id.to_string(),
+            name: org.name,
+            created_by: org.created_by,
+            role: OrgRole::Owner.as_str().to_string(),
+            created_at: org.created_at.to_rfc3339(),
+        }),
+    ))
+}
+
+/// `GET /api/orgs/{org_id}/members` — list members (owner/admin only).
+async fn organization_members_list_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(org_id): Path<String>,
+) -> Result<Json<OrganizationMembersListResponse>, StatusCode> {
+    let org_id = parse_org_id(&org_id)?;
+    let store = require_store(&state)?;
+    require_org_role(store, org_id, &principal.user_id, true).await?;
+    let rows = store.list_organization_members(org_id).await.map_err(|e| {
+        tracing::error!("list_organization_members failed: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(Json(OrganizationMembersListResponse {
+        org_id: org_id.to_string(),
+        members: rows.into_iter().map(member_response).collect(),
+    }))
+}
+
+/// `PUT /api/orgs/{org_id}/members/{member_user_id}` — add or update a
+/// member (owner/admin only; only owners may grant or change the owner role).
+/// Demoting the last owner is rejected with 409.
+async fn organization_member_upsert_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path((org_id, member_user_id)): Path<(String, String)>,
+    Json(body): Json<UpsertOrganizationMemberRequest>,
+) -> Result<Json<OrganizationMemberResponse>, StatusCode> {
+    let org_id = parse_org_id(&org_id)?;
+    let role = OrgRole::from_db_value(body.role.trim()).ok_or(StatusCode::BAD_REQUEST)?;
+    let store = require_store(&state)?;
+    let caller_role = require_org_role(store, org_id, &principal.user_id, true).await?;
+    let existing = store
+        .get_organization_role(org_id, &member_user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("get_organization_role failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if caller_role != OrgRole::Owner && (role == OrgRole::Owner || existing == Some(OrgRole::Owner))
+    {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    store
+        .get_user_account(&member_user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("get_user_account failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let record = store
+        .upsert_organization_member(org_id, &member_user_id, role)
+        .await
+        .map_err(|e| member_write_err("upsert_organization_member", e))?;
+    Ok(Json(member_response(record)))
+}
+
+/// `DELETE /api/orgs/{org_id}/members/{member_user_id}` — remove a member
+/// (owner/admin only; only owners may remove owners). Removing the last
+/// owner is rejected with 409.
+async fn organization_member_remove_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path((org_id, member_user_id)): Path<(String, String)>,
+) -> Result<StatusCode, StatusCode> {
+    let org_id = parse_org_id(&org_id)?;
+    let store = require_store(&state)?;
+    let caller_role = require_org_role(store, org_id, &principal.user_id, true).await?;
+    let existing = store
+        .get_organization_role(org_id, &member_user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("get_organization_role failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    if existing == OrgRole::Owner && caller_role != OrgRole::Owner {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    store
+        .remove_organization_member(org_id, &member_user_id)
+        .await
+        .map_err(|e| member_write_err("remove_organization_member", e))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /api/orgs/{org_id}/matters` — matters shared with the organization
+/// (any member).
+async fn organization_matters_list_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(org_id): Path<String>,
+) -> Result<Json<OrganizationMattersListResponse>, StatusCode> {
+    let org_id = parse_org_id(&org_id)?;
+    let store = require_store(&state)?;
+    require_org_role(store, org_id, &principal.user_id, false).await?;
+    let rows = store.list_organization_matters(org_id).await.map_err(|e| {
+        tracing::error!("list_organization_matters failed: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(Json(OrganizationMattersListResponse {
+        org_id: org_id.to_string(),
+        matters: rows
+            .into_iter()
+            .map(|r| OrganizationMatterResponse {
+                matter_owner_user_id: r.matter_owner_user_id,
+                matter_id: r.matter_id,
+            })
+            .collect(),
+    }))
+}
+
+/// `GET /api/orgs/{org_id}/clients` — clients shared with the organization
+/// (any member).
+async fn organization_clients_list_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(org_id): Path<String>,
+) -> Result<Json<ClientsListResponse>, StatusCode> {
+    let org_id = parse_org_id(&org_id)?;
+    let store = require_store(&state)?;
+    require_org_role(store, org_id, &principal.user_id, false).await?;
+    let rows = store.list_organization_clients(org_id).await.map_err(|e| {
+        tracing::error!("list_organization_clients failed: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(Json(ClientsListResponse {
+        clients: rows.into_iter().map(client_record_to_info).collect(),
+    }))
+}
+
+/// `PUT /api/matters/{id}/organization` — share a matter and its workspace
+/// documents with an organization, or make it private with `null`.
+///
+/// Requires matter Owner, plus owner/admin in the target organization.
+async fn matter_organization_set_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(body): Json<SetOrganizationRequest>,
+) -> Result<Json<SetOrganizationResponse>, StatusCode> {
+    let matter_id = crate::channels::web::server::sanitize_matter_id_for_route(&id)
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    require_matter_access(
+        &state.store,
+        &state.user_id,
+        &matter_id,
+        &principal.user_id,
+        MatterMemberRole::Owner,
+    )
+    .await?;
+    let org_id = body.org_id.as_deref().map(parse_org_id).transpose()?;
+    let store = require_store(&state)?;
+    if let Some(org_id) = org_id {
+        require_org_role(store, org_id, &principal.user_id, true).await?;
+    }
+    let updated = store
+        .set_matter_organization(&state.user_id, &matter_id, org_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("set_matter_organization failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if !updated {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let prefix = format!("{}/", matter_prefix_for_gateway(&state, &matter_id));
+    let documents_updated = store
+        .set_documents_organization(&state.user_id, &prefix, org_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("set_documents_organization failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok(Json(SetOrganizationResponse {
+        org_id: org_id.map(|id| id.to_string()),
+        documents_updated: Some(documents_updated),
+    }))
+}
+
+/// `PUT /api/clients/{id}/organization` — share a client record with an
+/// organization, or make it private with `null`.
+///
+/// Requires the client's owning user, plus owner/admin in the target
+/// organization.
+async fn client_organization_set_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(body): Json<SetOrganizationRequest>,
+) -> Result<Json<SetOrganizationResponse>, StatusCode> {
+    if principal.user_id != state.user_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let client_id = Uuid::parse_str(id.trim()).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let org_id = body.org_id.as_deref().map(parse_org_id).transpose()?;
+    let store = require_store(&state)?;
+    if let Some(org_id) = org_id {
+        require_org_role(store, org_id, &principal.user_id, true).await?;
+    }
+    let updated = store
+        .set_client_organization(&state.user_id, client_id, org_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("set_client_organization failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if !updated {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(Json(SetOrganizationResponse {
+        org_id: org_id.map(|id| id.to_string()),
+        documents_updated: None,
+    }))
+}
@@ -0,0 +1,195 @@
+//! Supervised-mode outbound review handlers.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, patch, post},
+};
+use uuid::Uuid;
+
+use crate::channels::web::auth::{AuthPrincipal, RequestPrincipal};
+use crate::channels::web::handlers::helpers::legal::record_legal_audit_event;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::channels::{DraftStatus, OutboundDraft, ReviewError, ReviewQueue};
+use crate::db::{AuditSeverity, UserRole};
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new()
+        .route("/api/review/drafts", get(review_drafts_list_handler))
+        .route("/api/review/drafts/{id}", patch(review_draft_edit_handler))
+        .route(
+            "/api/review/drafts/{id}/approve",
+            post(review_draft_approve_handler),
+        )
+        .route(
+            "/api/review/drafts/{id}/reject",
+            post(review_draft_reject_handler),
+        )
+}
+
+fn draft_to_info(draft: OutboundDraft) -> OutboundDraftInfo {
+    OutboundDraftInfo {
+        id: draft.id.to_string(),
+        kind: draft.kind(),
+        edited: draft.edited(),
+        channel: draft.channel,
+        recipient: draft.recipient,
+        thread_id: draft.thread_id,
+        content: draft.content,
+        original_content: draft.original_content,
+        status: draft.status.as_str(),
+        created_at: draft.created_at.to_rfc3339(),
+        decided_at: draft.decided_at.map(|t| t.to_rfc3339()),
+        decided_by: draft.decided_by,
+        rejection_reason: draft.rejection_reason,
+    }
+}
+
+/// Drafts can contain privileged client communications, so only admins and
+/// attorneys may see or act on them.
+fn require_reviewer(principal: &AuthPrincipal) -> Result<(), (StatusCode, String)> {
+    match principal.role {
+        UserRole::Admin | UserRole::Attorney => Ok(()),
+        UserRole::Staff | UserRole::Viewer => Err((
+            StatusCode::FORBIDDEN,
+            "Only attorneys and administrators can review outbound messages".to_string(),
+        )),
+    }
+}
+
+fn review_queue(state: &GatewayState) -> Result<&Arc<ReviewQueue>, (StatusCode, String)> {
+    state.review_queue.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Review queue not available".to_string(),
+    ))
+}
+
+fn parse_draft_id(raw: &str) -> Result<Uuid, (StatusCode, String)> {
+    Uuid::parse_str(raw).map_err(|_| (StatusCode::BAD_REQUEST, "Invalid draft ID".to_string()))
+}
+
+fn review_error(err: ReviewError) -> (StatusCode, String) {
+    let status = match err {
+        ReviewError::NotFound(_) => StatusCode::NOT_FOUND,
+        ReviewError::AlreadyDecided { .. } => StatusCode::CONFLICT,
+        ReviewError::EmptyContent => StatusCode::BAD_REQUEST,
+        ReviewError::Delivery(_) => StatusCode::BAD_GATEWAY,
+    };
+    (status, err.to_string())
+}
+
+async fn audit_review(
+    state: &GatewayState,
+    event_type: &str,
+    actor: &str,
+    draft: &OutboundDraft,
+    extra: serde_json::Value,
+) {
+    let mut details = serde_json::json!({
+        "draft_id": draft.id.to_string(),
+        "channel": draft.channel,
+        "recipient": draft.recipient,
+        "kind": draft.kind(),
+        "edited": draft.edited(),
+    });
+    if let (Some(details), serde_json::Value::Object(extra)) = (details.as_object_mut(), extra) {
+        details.extend(extra);
+    }
+    record_legal_audit_event(state, event_type, actor, None, AuditSeverity::Info, details).await;
+}
+
+pub(crate) async fn review_drafts_list_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Query(query): Query<OutboundDraftListQuery>,
+) -> Result<Json<OutboundDraftListResponse>, (StatusCode, String)> {
+    require_reviewer(&principal)?;
+    let queue = review_queue(&state)?;
+    let status = match query.status.as_deref().map(str::trim) {
+        None | Some("") => Some(DraftStatus::Pending),
+        Some("all") => None,
+        Some(raw) => Some(
+            raw.parse::<DraftStatus>()
+                .map_err(|e| (StatusCode::BAD_REQUEST, e))?,
+        ),
+    };
+    let drafts = queue.list(status).await;
+    Ok(Json(OutboundDraftListResponse {
+        supervised_mode: queue.enabled(),
+        pending: queue.pending_count().await,
+        drafts: drafts.into_iter().map(draft_to_info).collect(),
+    }))
+}
+
+pub(crate) async fn review_draft_edit_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<OutboundDraftEditRequest>,
+) -> Result<Json<OutboundDraftInfo>, (StatusCode, String)> {
+    require_reviewer(&principal)?;
+    let queue = review_queue(&state)?;
+    let draft = queue
+        .edit(parse_draft_id(&id)?, req.content)
+        .await
+        .map_err(review_error)?;
+    audit_review(
+        &state,
+        "outbound_draft_edited",
+        &principal.user_id,
+        &draft,
+        serde_json::json!({}),
+    )
+    .await;
+    Ok(Json(draft_to_info(draft)))
+}
+
+pub(crate) async fn review_draft_approve_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<OutboundDraftApproveRequest>,
+) -> Result<Json<OutboundDraftInfo>, (StatusCode, String)> {
+    require_reviewer(&principal)?;
+    let queue = review_queue(&state)?;
+    let draft = queue
+        .approve(parse_draft_id(&id)?, &principal.user_id, req.content)
+        .await
+        .map_err(review_error)?;
+    audit_review(
+        &state,
+        "outbound_draft_approved",
+        &principal.user_id,
+        &draft,
+        serde_json::json!({}),
+    )
+    .await;
+    Ok(Json(draft_to_info(draft)))
+}
+
+pub(crate) async fn review_draft_reject_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<OutboundDraftRejectRequest>,
+) -> Result<Json<OutboundDraftInfo>, (StatusCode, String)> {
+    require_reviewer(&principal)?;
+    let queue = review_queue(&state)?;
+    let draft = queue
+        .reject(parse_draft_id(&id)?, &principal.user_id, req.reason)
+        .await
+        .map_err(review_error)?;
+    audit_review(
+        &state,
+        "outbound_draft_rejected",
+        &principal.user_id,
+        &draft,
+        serde_json::json!({ "reason": draft.rejection_reason.clone() }),
+    )
+    .await;
+    Ok(Json(draft_to_info(draft)))
+}
@@ -9,6 +9,8 @@ use crate::channels::web::state::GatewayState;
 
 pub fn public_routes() -> Router<Arc<GatewayState>> {
     super::gateway::public_routes()
+        .merge(super::routines::public_routes())
+        .merge(super::email::public_routes())
 }
 
 pub fn static_routes() -> Router<Arc<GatewayState>> {
@@ -24,16 +26,25 @@ pub fn protected_feature_routes() -> Router<Arc<GatewayState>> {
     Router::new()
         .merge(super::chat::routes())
         .merge(super::memory::routes())
+        .merge(super::shares::routes())
         .merge(super::matters::routes())
+        .merge(super::dashboard::routes())
+        .merge(super::search::routes())
         .merge(super::legal::routes())
         .merge(super::jobs::routes())
         .merge(super::logs::routes())
+        .merge(super::notifications::routes())
+        .merge(super::organizations::routes())
         .merge(super::extensions::routes())
         .merge(super::pairing::routes())
+        .merge(super::review::routes())
+        .merge(super::integrations::routes())
         .merge(super::routines::routes())
         .merge(super::settings::routes())
         .merge(super::backups::routes())
         .merge(super::gateway::routes())
         .merge(super::skills::routes())
+        .merge(super::templates::routes())
         .merge(super::users::routes())
+        .merge(super::watchlist::routes())
 }
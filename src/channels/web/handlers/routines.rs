@@ -6,15 +6,26 @@ use axum::{
     Json, Router,
     extract::{Path, State},
     http::StatusCode,
+    response::{IntoResponse, Response},
     routing::{get, post},
 };
 use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::channels::IncomingMessage;
+use crate::channels::web::handlers::helpers::intake::intake_error_response;
 use crate::channels::web::state::GatewayState;
 use crate::channels::web::types::*;
 
+/// Routes that authenticate with a per-routine URL token instead of the
+/// gateway bearer token, so external systems can call them.
+pub fn public_routes() -> Router<Arc<GatewayState>> {
+    Router::new().route(
+        "/api/routines/{id}/webhook/{token}",
+        post(routines_webhook_handler),
+    )
+}
+
 pub fn routes() -> Router<Arc<GatewayState>> {
     Router::new()
         .route(
@@ -22,6 +33,10 @@ pub fn routes() -> Router<Arc<GatewayState>> {
             get(routines_list_handler).post(routines_create_handler),
         )
         .route("/api/routines/summary", get(routines_summary_handler))
+        .route(
+            "/api/routines/client-status-reports",
+            post(routines_install_status_reports_handler),
+        )
         .route("/api/routines/{id}", get(routines_detail_handler))
         .route("/api/routines/{id}/trigger", post(routines_trigger_handler))
         .route("/api/routines/{id}/toggle", post(routines_toggle_handler))
@@ -135,9 +150,11 @@ async fn routines_detail_handler(
         description: routine.description.clone(),
         enabled: routine.enabled,
         trigger: serde_json::to_value(&routine.trigger).unwrap_or_default(),
+        webhook_path: webhook_path(&routine),
         action: serde_json::to_value(&routine.action).unwrap_or_default(),
         guardrails: serde_json::to_value(&routine.guardrails).unwrap_or_default(),
         notify: serde_json::to_value(&routine.notify).unwrap_or_default(),
+        chain: routine.chain.to_json(),
         last_run_at: routine.last_run_at.map(|dt| dt.to_rfc3339()),
         next_fire_at: routine.next_fire_at.map(|dt| dt.to_rfc3339()),
         run_count: routine.run_count,
@@ -150,20 +167,23 @@ async fn routines_detail_handler(
 async fn routines_trigger_handler(
     State(state): State<Arc<GatewayState>>,
     Path(id): Path<String>,
-) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
-    let store = state.store.as_ref().ok_or((
-        StatusCode::SERVICE_UNAVAILABLE,
-        "Database not available".to_string(),
-    ))?;
+) -> Result<Json<serde_json::Value>, Response> {
+    let store = state.store.as_ref().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Database not available".to_string(),
+        )
+            .into_response()
+    })?;
 
     let routine_id = Uuid::parse_str(&id)
-        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid routine ID".to_string()))?;
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid routine ID".to_string()).into_response())?;
 
     let routine = store
         .get_routine(routine_id)
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((StatusCode::NOT_FOUND, "Routine not found".to_string()))?;
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response())?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Routine not found".to_string()).into_response())?;
 
     // Send the routine prompt through the message pipeline as a manual trigger.
     let prompt = match &routine.action {
@@ -171,23 +191,45 @@ async fn routines_trigger_handler(
         crate::agent::routine::RoutineAction::FullJob {
             title, description, ..
         } => format!("{}: {}", title, description),
+        // Status reports are compiled from matter records, not by the agent,
+        // so they run on the routine engine directly.
+        crate::agent::routine::RoutineAction::MatterStatusReport { .. } => {
+            use crate::error::RoutineError;
+
+            let engine = state
+                .routine_engine
+                .as_ref()
+                .and_then(|slot| slot.get())
+                .ok_or_else(|| {
+                    (
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        "Routine engine not running".to_string(),
+                    )
+                        .into_response()
+                })?;
+            let run_id = engine.fire_manual(routine_id).await.map_err(|e| {
+                let status = match e {
+                    RoutineError::Disabled { .. } => StatusCode::CONFLICT,
+                    RoutineError::MaxConcurrent { .. } => StatusCode::TOO_MANY_REQUESTS,
+                    _ => StatusCode::INTERNAL_SERVER_ERROR,
+                };
+                (status, e.to_string()).into_response()
+            })?;
+            return Ok(Json(serde_json::json!({
+                "status": "triggered",
+                "routine_id": routine_id,
+                "run_id": run_id,
+            })));
+        }
     };
 
     let content = format!("[routine:{}] {}", routine.name, prompt);
     let msg = IncomingMessage::new("gateway", &state.user_id, content);
 
-    let tx_guard = state.msg_tx.read().await;
-    let tx = tx_guard.as_ref().ok_or((
-        StatusCode::SERVICE_UNAVAILABLE,
-        "Channel not started".to_string(),
-    ))?;
-
-    tx.send(msg).await.map_err(|_| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Channel closed".to_string(),
-        )
-    })?;
+    state
+        .enqueue_message(msg)
+        .await
+        .map_err(intake_error_response)?;
 
     Ok(Json(serde_json::json!({
         "status": "triggered",
@@ -195,6 +237,82 @@ async fn routines_trigger_handler(
     })))
 }
 
+fn webhook_path(routine: &crate::agent::routine::Routine) -> Option<String> {
+    match &routine.trigger {
+        crate::agent::routine::Trigger::Webhook {
+            secret: Some(token),
+            ..
+        } if !token.is_empty() => Some(format!("/api/routines/{}/webhook/{token}", routine.id)),
+        _ => None,
+    }
+}
+
+/// `POST /api/routines/{id}/webhook/{token}` — fire a webhook-triggered
+/// routine with the posted JSON in its prompt context.
+///
+/// Unknown routines, non-webhook routines, and wrong tokens all return 404 so
+/// callers cannot probe for routine IDs.
+pub(crate) async fn routines_webhook_handler(
+    State(state): State<Arc<GatewayState>>,
+    Path((id, token)): Path<(String, String)>,
+    body: axum::body::Bytes,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, String)> {
+    use crate::error::RoutineError;
+
+    let not_found = || (StatusCode::NOT_FOUND, "Routine not found".to_string());
+    let routine_id = Uuid::parse_str(&id).map_err(|_| not_found())?;
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let routine = store
+        .get_routine(routine_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .filter(|r| r.user_id == state.user_id && r.trigger.accepts_webhook_token(&token))
+        .ok_or_else(not_found)?;
+
+    let payload = if body.iter().all(u8::is_ascii_whitespace) {
+        serde_json::Value::Null
+    } else {
+        serde_json::from_slice(&body).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Webhook body must be JSON: {e}"),
+            )
+        })?
+    };
+
+    let engine = state
+        .routine_engine
+        .as_ref()
+        .and_then(|slot| slot.get())
+        .ok_or((
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Routine engine not running".to_string(),
+        ))?;
+    let run_id = engine
+        .fire_webhook(routine, payload)
+        .await
+        .map_err(|e| match e {
+            RoutineError::Disabled { .. } => (StatusCode::CONFLICT, e.to_string()),
+            RoutineError::Skipped { .. } | RoutineError::MaxConcurrent { .. } => {
+                (StatusCode::TOO_MANY_REQUESTS, e.to_string())
+            }
+            RoutineError::NotWebhook { .. } => not_found(),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        })?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({
+            "status": "accepted",
+            "routine_id": routine_id,
+            "run_id": run_id,
+        })),
+    ))
+}
+
 #[derive(Deserialize)]
 struct ToggleRequest {
     enabled: Option<bool>,
@@ -302,9 +420,12 @@ async fn routines_runs_handler(
 /// Convert a Routine to the trimmed RoutineInfo for list display.
 fn routine_to_info(r: &crate::agent::routine::Routine) -> RoutineInfo {
     let (trigger_type, trigger_summary) = match &r.trigger {
-        crate::agent::routine::Trigger::Cron { schedule } => {
-            ("cron".to_string(), format!("cron: {}", schedule))
-        }
+        crate::agent::routine::Trigger::Cron {
+            schedule, timezone, ..
+        } => match timezone {
+            Some(tz) => ("cron".to_string(), format!("cron: {} ({})", schedule, tz)),
+            None => ("cron".to_string(), format!("cron: {}", schedule)),
+        },
         crate::agent::routine::Trigger::Event {
             pattern, channel, ..
         } => {
@@ -321,6 +442,7 @@ fn routine_to_info(r: &crate::agent::routine::Routine) -> RoutineInfo {
     let action_type = match &r.action {
         crate::agent::routine::RoutineAction::Lightweight { .. } => "lightweight",
         crate::agent::routine::RoutineAction::FullJob { .. } => "full_job",
+        crate::agent::routine::RoutineAction::MatterStatusReport { .. } => "matter_status_report",
     };
 
     let status = if !r.enabled {
@@ -344,16 +466,41 @@ fn routine_to_info(r: &crate::agent::routine::Routine) -> RoutineInfo {
         run_count: r.run_count,
         consecutive_failures: r.consecutive_failures,
         status: status.to_string(),
+        urgent: r.guardrails.urgent,
     }
 }
 
+/// Install the weekly client status report routine. Idempotent: returns the
+/// existing routine with 200 when it is already installed.
+async fn routines_install_status_reports_handler(
+    State(state): State<Arc<GatewayState>>,
+) -> Result<(StatusCode, Json<RoutineInfo>), (StatusCode, String)> {
+    use crate::legal::status_report::{ROUTINE_NAME, weekly_status_routine};
+
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    if let Some(existing) = store
+        .get_routine_by_name(&state.user_id, ROUTINE_NAME)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    {
+        return Ok((StatusCode::OK, Json(routine_to_info(&existing))));
+    }
+    let routine = weekly_status_routine(&state.user_id);
+    store
+        .create_routine(&routine)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok((StatusCode::CREATED, Json(routine_to_info(&routine))))
+}
+
 async fn routines_create_handler(
     State(state): State<Arc<GatewayState>>,
     Json(req): Json<RoutineCreateRequest>,
 ) -> Result<(StatusCode, Json<RoutineInfo>), (StatusCode, String)> {
-    use crate::agent::routine::{
-        NotifyConfig, Routine, RoutineAction, RoutineGuardrails, Trigger, next_cron_fire,
-    };
+    use crate::agent::routine::{NotifyConfig, Routine, RoutineAction, RoutineGuardrails, Trigger};
 
     let store = state.store.as_ref().ok_or((
         StatusCode::SERVICE_UNAVAILABLE,
@@ -372,15 +519,12 @@ async fn routines_create_handler(
                 StatusCode::BAD_REQUEST,
                 "schedule is required for cron trigger".to_string(),
             ))?;
-            next_cron_fire(schedule).map_err(|e| {
-                (
-                    StatusCode::BAD_REQUEST,
-                    format!("invalid cron schedule: {e}"),
-                )
-            })?;
-            Trigger::Cron {
-                schedule: schedule.to_string(),
-            }
+            Trigger::cron_from_input(
+                schedule,
+                req.timezone.as_deref(),
+                req.jitter_secs.unwrap_or(0),
+            )
+            .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?
         }
         "event" => {
             let pattern = req.event_pattern.as_deref().ok_or((
@@ -396,7 +540,7 @@ async fn routines_create_handler(
         }
         "webhook" => Trigger::Webhook {
             path: None,
-            secret: None,
+            secret: Some(crate::agent::routine::generate_webhook_token()),
         },
         "manual" => Trigger::Manual,
         other => {
@@ -408,11 +552,7 @@ async fn routines_create_handler(
     };
 
     // Compute next fire time for cron triggers
-    let next_fire = if let Trigger::Cron { ref schedule } = trigger {
-        next_cron_fire(schedule).unwrap_or(None)
-    } else {
-        None
-    };
+    let next_fire = trigger.next_fire().unwrap_or(None);
 
     // Build action
     let action_type = req.action_type.as_deref().unwrap_or("lightweight");
@@ -428,6 +568,28 @@ async fn routines_create_handler(
             description: req.prompt.clone(),
             max_iterations: 10,
         },
+        "matter_status_report" => {
+            let matter_id = req
+                .matter_id
+                .as_deref()
+                .and_then(crate::legal::policy::sanitize_optional_matter_id);
+            let period_days = req
+                .period_days
+                .unwrap_or_else(crate::agent::routine::default_report_period_days);
+            if !(1..=90).contains(&period_days) {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    "period_days must be between 1 and 90".to_string(),
+                ));
+            }
+            let note = req.prompt.trim();
+            RoutineAction::MatterStatusReport {
+                matter_id,
+                note: (!note.is_empty()).then(|| note.to_string()),
+                period_days,
+                send: req.send.unwrap_or(false),
+            }
+        }
         other => {
             return Err((
                 StatusCode::BAD_REQUEST,
@@ -450,8 +612,11 @@ async fn routines_create_handler(
             cooldown: std::time::Duration::from_secs(cooldown_secs),
             max_concurrent: 1,
             dedup_window: None,
+            urgent: req.urgent.unwrap_or(false),
+            execution: req.execution.unwrap_or_default(),
         },
         notify: NotifyConfig::default(),
+        chain: req.chain.unwrap_or_default(),
         last_run_at: None,
         next_fire_at: next_fire,
         run_count: 0,
@@ -461,6 +626,15 @@ async fn routines_create_handler(
         updated_at: now,
     };
 
+    if !routine.chain.is_empty() {
+        let existing = store
+            .list_routines(&state.user_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        crate::agent::routine::check_chain(&routine, &existing)
+            .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    }
+
     store
         .create_routine(&routine)
         .await
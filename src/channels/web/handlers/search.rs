@@ -0,0 +1,360 @@
+//! Cross-matter search handler.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    http::StatusCode,
+    routing::get,
+};
+
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+
+const DEFAULT_SEARCH_LIMIT: usize = 25;
+const MAX_SEARCH_LIMIT: usize = 100;
+const SNIPPET_CHARS: usize = 160;
+
+/// Result kinds accepted by the `types` filter.
+const SEARCH_KINDS: [&str; 7] = [
+    "matter",
+    "client",
+    "document",
+    "task",
+    "note",
+    "deadline",
+    "time_entry",
+];
+
+/// Words too common to count toward a match.
+const STOP_WORDS: &[&str] = &[
+    "a", "about", "an", "and", "at", "by", "for", "from", "in", "is", "of", "on", "or", "that",
+    "the", "to", "with",
+];
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new().route("/api/search", get(global_search_handler))
+}
+
+/// Lowercased query terms with stop words removed.
+fn search_terms(query: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|term| !term.is_empty() && !STOP_WORDS.contains(&term.as_str()))
+        .filter(|term| seen.insert(term.clone()))
+        .collect()
+}
+
+/// Score a record against the query: each term counts fully when it appears
+/// in the title and partly when only in the body, with a bonus for the whole
+/// phrase. `None` when nothing matches.
+fn score_record(terms: &[String], phrase: &str, title: &str, body: &str) -> Option<f64> {
+    let title = title.to_lowercase();
+    let body = body.to_lowercase();
+    let mut score = 0.0;
+    for term in terms {
+        if title.contains(term.as_str()) {
+            score += 1.0;
+        } else if body.contains(term.as_str()) {
+            score += 0.6;
+        }
+    }
+    if score == 0.0 {
+        return None;
+    }
+    score /= terms.len() as f64;
+    if title.contains(phrase) || body.contains(phrase) {
+        score += 0.25;
+    }
+    Some(score.min(1.0))
+}
+
+/// Up to `SNIPPET_CHARS` of `text` around the first matching term.
+fn snippet(text: &str, terms: &[String]) -> Option<String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let lower = text.to_lowercase();
+    let start = terms
+        .iter()
+        .filter_map(|term| lower.find(term.as_str()))
+        .min()
+        .unwrap_or(0);
+    // Lowercasing can shift byte offsets; count characters instead.
+    let char_start = lower[..start.min(lower.len())].chars().count();
+    let skip = char_start.saturating_sub(SNIPPET_CHARS / 4);
+    let mut out: String = text.chars().skip(skip).take(SNIPPET_CHARS).collect();
+    if skip > 0 {
+        out.insert(0, '…');
+    }
+    if text.chars().count() > skip + SNIPPET_CHARS {
+        out.push('…');
+    }
+    Some(out)
+}
+
+fn parse_search_kinds(raw: Option<&str>) -> Result<HashSet<&'static str>, (StatusCode, String)> {
+    let Some(raw) = raw.filter(|raw| !raw.trim().is_empty()) else {
+        return Ok(SEARCH_KINDS.into_iter().collect());
+    };
+    raw.split(',')
+        .map(str::trim)
+        .filter(|kind| !kind.is_empty())
+        .map(|kind| {
+            SEARCH_KINDS
+                .into_iter()
+                .find(|known| known.eq_ignore_ascii_case(kind))
+                .ok_or_else(|| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        format!("'types' must be a list of: {}", SEARCH_KINDS.join(", ")),
+                    )
+                })
+        })
+        .collect()
+}
+
+/// `GET /api/search?q=` — one ranked result set across matters, clients,
+/// workspace documents (hybrid search), tasks, notes, deadlines, and time
+/// entry narratives, with per-kind facet counts.
+pub(crate) async fn global_search_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Query(query): Query<GlobalSearchQuery>,
+) -> Result<Json<GlobalSearchResponse>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    // Same rule as the matter list: only the owner searches every matter.
+    if principal.user_id != state.user_id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Insufficient permissions".to_string(),
+        ));
+    }
+    let phrase = query.q.trim().to_lowercase();
+    let terms = search_terms(&phrase);
+    if terms.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'q' must contain at least one search term".to_string(),
+        ));
+    }
+    let kinds = parse_search_kinds(query.types.as_deref())?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+    let db_err =
+        |e: crate::error::DatabaseError| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+
+    let mut hits: Vec<GlobalSearchHit> = Vec::new();
+    let mut push = |kind: &str, id: String, matter_id: Option<&str>, title: &str, body: &str| {
+        if let Some(score) = score_record(&terms, &phrase, title, body) {
+            hits.push(GlobalSearchHit {
+                kind: kind.to_string(),
+                id,
+                matter_id: matter_id.map(str::to_string),
+                title: title.to_string(),
+                snippet: snippet(body, &terms),
+                path: None,
+                score,
+            });
+        }
+    };
+
+    let clients = store
+        .list_clients(&state.user_id, None)
+        .await
+        .map_err(db_err)?;
+    let client_names: HashMap<_, _> = clients.iter().map(|c| (c.id, c.name.clone())).collect();
+    if kinds.contains("client") {
+        for client in &clients {
+            let body = [client.email.as_deref(), client.notes.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join("\n");
+            push("client", client.id.to_string(), None, &client.name, &body);
+        }
+    }
+
+    let matters = store
+        .list_matters_db(&state.user_id)
+        .await
+        .map_err(db_err)?;
+    let custom_field_schemas =
+        crate::legal::custom_fields::resolve_schemas(state.store.as_ref(), &state.user_id).await;
+    for matter in &matters {
+        let matter_id = matter.matter_id.as_str();
+        if kinds.contains("matter") {
+            let mut body = [
+                client_names.get(&matter.client_id).map(String::as_str),
+                matter.practice_area.as_deref(),
+                matter.jurisdiction.as_deref(),
+                matter.stage.as_deref(),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" · ");
+            // Searchable custom fields follow on their own lines.
+            let custom = crate::legal::custom_fields::search_text(
+                &crate::legal::custom_fields::fields_for_practice_area(
+                    &custom_field_schemas,
+                    matter.practice_area.as_deref(),
+                ),
+                &matter.custom_fields,
+            );
+            if !custom.is_empty() {
+                body.push('\n');
+                body.push_str(&custom);
+            }
+            push(
+                "matter",
+                matter_id.to_string(),
+                Some(matter_id),
+                matter_id,
+                &body,
+            );
+        }
+        if kinds.contains("task") {
+            for task in store
+                .list_matter_tasks(&state.user_id, matter_id)
+                .await
+                .map_err(db_err)?
+            {
+                let body = task.description.clone().unwrap_or_default();
+                push(
+                    "task",
+                    task.id.to_string(),
+                    Some(matter_id),
+                    &task.title,
+                    &body,
+                );
+            }
+        }
+        if kinds.contains("note") {
+            for note in store
+                .list_matter_notes(&state.user_id, matter_id)
+                .await
+                .map_err(db_err)?
+            {
+                let title = note.body.lines().next().unwrap_or_default();
+                push(
+                    "note",
+                    note.id.to_string(),
+                    Some(matter_id),
+                    title,
+                    &note.body,
+                );
+            }
+        }
+        if kinds.contains("deadline") {
+            for deadline in store
+                .list_matter_deadlines(&state.user_id, matter_id)
+                .await
+                .map_err(db_err)?
+            {
+                let body = [
+                    Some(deadline.deadline_type.as_str()),
+                    deadline.rule_ref.as_deref(),
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" · ");
+                push(
+                    "deadline",
+                    deadline.id.to_string(),
+                    Some(matter_id),
+                    &deadline.title,
+                    &body,
+                );
+            }
+        }
+        if kinds.contains("time_entry") {
+            for entry in store
+                .list_time_entries(&state.user_id, matter_id)
+                .await
+                .map_err(db_err)?
+            {
+                let title = format!(
+                    "{} · {}h · {}",
+                    entry.timekeeper, entry.hours, entry.entry_date
+                );
+                push(
+                    "time_entry",
+                    entry.id.to_string(),
+                    Some(matter_id),
+                    &title,
+                    &entry.description,
+                );
+            }
+        }
+    }
+
+    if kinds.contains("document")
+        && let Some(workspace) = state.workspace.as_ref()
+    {
+        let matter_root = crate::channels::web::server::matter_root_for_gateway(state.as_ref());
+        // Search on the filtered terms so stop words and punctuation in a
+        // natural-language query don't defeat the full-text match.
+        let results = workspace
+            .search_with_config(
+                &terms.join(" "),
+                crate::workspace::SearchConfig::default().with_limit(MAX_SEARCH_LIMIT),
+            )
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        // Keep the best chunk per document.
+        let mut seen = HashSet::new();
+        for result in results {
+            if !seen.insert(result.path.clone()) {
+                continue;
+            }
+            let matter_id = result
+                .path
+                .strip_prefix(&format!("{matter_root}/"))
+                .and_then(|rest| rest.split('/').next())
+                .filter(|id| !id.is_empty());
+            let title = result.path.rsplit('/').next().unwrap_or(&result.path);
+            hits.push(GlobalSearchHit {
+                kind: "document".to_string(),
+                id: result.path.clone(),
+                matter_id: matter_id.map(str::to_string),
+                title: title.to_string(),
+                snippet: snippet(&result.content, &terms),
+                path: Some(result.path.clone()),
+                score: f64::from(result.score).clamp(0.0, 1.0),
+            });
+        }
+    }
+
+    let mut facets = BTreeMap::new();
+    for hit in &hits {
+        *facets.entry(hit.kind.clone()).or_insert(0) += 1;
+    }
+    let total = hits.len();
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.kind.cmp(&b.kind))
+            .then_with(|| a.title.cmp(&b.title))
+    });
+    hits.truncate(limit);
+
+    Ok(Json(GlobalSearchResponse {
+        query: query.q.trim().to_string(),
+        facets,
+        total,
+        results: hits,
+    }))
+}
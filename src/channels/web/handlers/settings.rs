@@ -18,6 +18,31 @@ fn validate_setting_write(key: &str, value: &serde_json::Value) -> Result<(), St
     {
         return Err(StatusCode::BAD_REQUEST);
     }
+    if key == crate::agent::execution_window::EXECUTION_POLICY_SETTING_KEY
+        && crate::agent::execution_window::parse_setting_value(value).is_err()
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if key == crate::legal::stages::STAGE_WORKFLOWS_SETTING_KEY
+        && crate::legal::stages::parse_setting_value(value).is_err()
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if key == crate::legal::email_intake::EMAIL_INTAKE_SETTING_KEY
+        && crate::legal::email_intake::parse_setting_value(value).is_err()
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if key == crate::legal::custom_fields::CUSTOM_FIELDS_SETTING_KEY
+        && crate::legal::custom_fields::parse_setting_value(value).is_err()
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if key == crate::legal::readability::PLAIN_LANGUAGE_SETTING_KEY
+        && crate::legal::readability::parse_setting_value(value).is_err()
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
     Ok(())
 }
 
@@ -31,6 +56,10 @@ pub fn routes() -> Router<Arc<GatewayState>> {
             "/api/settings/skeptical_mode/resolved",
             get(skeptical_mode_resolved_handler),
         )
+        .route(
+            "/api/settings/custom-fields",
+            get(custom_field_schemas_get_handler).put(custom_field_schemas_put_handler),
+        )
         .route("/api/settings/{key}", get(settings_get_handler))
         .route(
             "/api/settings/{key}",
@@ -198,3 +227,41 @@ async fn skeptical_mode_resolved_handler(
 
     Json(SkepticalModeResolvedResponse { enabled })
 }
+
+/// `GET /api/settings/custom-fields` — the firm's matter custom field
+/// schemas (an empty list when none are configured).
+pub(crate) async fn custom_field_schemas_get_handler(
+    State(state): State<Arc<GatewayState>>,
+) -> Result<Json<CustomFieldSchemasPayload>, (StatusCode, String)> {
+    let schemas =
+        crate::legal::custom_fields::resolve_schemas(state.store.as_ref(), &state.user_id).await;
+    let schemas = serde_json::to_value(&schemas)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(CustomFieldSchemasPayload { schemas }))
+}
+
+/// `PUT /api/settings/custom-fields` — validate and replace the schemas.
+/// Existing matter values are not rewritten; they are checked against the
+/// new schemas the next time the matter is updated.
+pub(crate) async fn custom_field_schemas_put_handler(
+    State(state): State<Arc<GatewayState>>,
+    Json(body): Json<CustomFieldSchemasPayload>,
+) -> Result<Json<CustomFieldSchemasPayload>, (StatusCode, String)> {
+    let store = state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))?;
+    let schemas = crate::legal::custom_fields::parse_setting_value(&body.schemas)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let value = serde_json::to_value(&schemas)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    store
+        .set_setting(
+            &state.user_id,
+            crate::legal::custom_fields::CUSTOM_FIELDS_SETTING_KEY,
+            &value,
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(CustomFieldSchemasPayload { schemas: value }))
+}
@@ -0,0 +1,639 @@
+//! External share links for workspace documents.
+//!
+//! `POST /api/memory/share` mints an expiring, optionally password-protected
+//! link to one markdown document that co-counsel can open without a gateway
+//! token. Only a hash of the URL token and a PBKDF2 hash of the password are
+//! stored. Every download (and every rejected password) is written to the
+//! legal audit log. Past [`FREE_PASSWORD_ATTEMPTS`] wrong passwords each
+//! further guess must wait out a growing delay, on top of the per-address
+//! limit on share routes. Downloaded copies carry a confidentiality banner
+//! unless the link was created without one.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{delete, get},
+};
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+use uuid::Uuid;
+
+use crate::channels::web::auth::{RequestPrincipal, hash_auth_token};
+use crate::channels::web::handlers::memory::{matter_for_path, require_document_access};
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{
+    AuditSeverity, Database, DocumentShareLinkRecord, MatterMemberRole, NewDocumentShareLinkParams,
+};
+
+const DEFAULT_EXPIRY_HOURS: i64 = 72;
+const MAX_EXPIRY_HOURS: i64 = 30 * 24;
+const MIN_PASSWORD_LEN: usize = 8;
+const MAX_RECIPIENT_LEN: usize = 200;
+/// Wrong passwords allowed before guesses on a link are slowed down.
+pub(crate) const FREE_PASSWORD_ATTEMPTS: i64 = 3;
+/// Wait after the first failure past [`FREE_PASSWORD_ATTEMPTS`]; doubles
+/// with each further failure up to [`MAX_PASSWORD_BACKOFF_SECS`].
+const PASSWORD_BACKOFF_BASE_SECS: i64 = 30;
+const MAX_PASSWORD_BACKOFF_SECS: i64 = 15 * 60;
+
+const PASSWORD_HASH_SCHEME: &str = "pbkdf2-sha256";
+#[cfg(not(test))]
+const PBKDF2_ITERATIONS: u32 = 600_000;
+// Unoptimized test builds would spend seconds per hash at the real cost;
+// the stored hash records its own iteration count either way.
+#[cfg(test)]
+const PBKDF2_ITERATIONS: u32 = 1_000;
+
+pub fn public_routes() -> Router<Arc<GatewayState>> {
+    Router::new().route(
+        "/api/share/{token}",
+        get(share_download_handler).post(share_download_with_password_handler),
+    )
+}
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new()
+        .route(
+            "/api/memory/share",
+            get(share_links_list_handler).post(share_link_create_handler),
+        )
+        .route("/api/memory/share/{id}", delete(share_link_revoke_handler))
+}
+
+fn require_store(state: &GatewayState) -> Result<&Arc<dyn Database>, (StatusCode, String)> {
+    state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))
+}
+
+/// Random URL token for a new share link.
+fn generate_share_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill(&mut bytes);
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// PBKDF2-HMAC-SHA256 with a 32-byte output.
+fn pbkdf2_sha256(password: &[u8], salt: &[u8], iterations: u32) -> [u8; 32] {
+    pbkdf2::pbkdf2_hmac_array::<Sha256, 32>(password, salt, iterations)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Hash a link password, stored as
+/// `pbkdf2-sha256$<iterations>$<salt hex>$<digest hex>`.
+fn hash_share_password(password: &str) -> String {
+    let mut salt = [0u8; 16];
+    rand::thread_rng().fill(&mut salt);
+    let digest = pbkdf2_sha256(password.as_bytes(), &salt, PBKDF2_ITERATIONS);
+    format!(
+        "{PASSWORD_HASH_SCHEME}${PBKDF2_ITERATIONS}${}${}",
+        to_hex(&salt),
+        to_hex(&digest)
+    )
+}
+
+fn verify_share_password(stored: &str, password: &str) -> bool {
+    let mut parts = stored.split('$');
+    let (Some(PASSWORD_HASH_SCHEME), Some(iterations), Some(salt), Some(digest), None) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) else {
+        return false;
+    };
+    let Ok(iterations) = iterations.parse::<u32>() else {
+        return false;
+    };
+    let Some(salt) = (0..salt.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(salt.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()
+    else {
+        return false;
+    };
+    if iterations == 0 || salt.is_empty() {
+        return false;
+    }
+    let candidate = to_hex(&pbkdf2_sha256(password.as_bytes(), &salt, iterations));
+    candidate.as_bytes().ct_eq(digest.as_bytes()).into()
+}
+
+/// Run a password hash or check off the async runtime; PBKDF2 is slow on
+/// purpose.
+async fn blocking_password_op<T: Send + 'static>(
+    op: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, (StatusCode, String)> {
+    tokio::task::spawn_blocking(op)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// Seconds a caller must still wait before the next password guess on
+/// `link`, if it is backing off.
+fn password_retry_after(link: &DocumentShareLinkRecord, now: DateTime<Utc>) -> Option<i64> {
+    let excess = link.failed_password_attempts - FREE_PASSWORD_ATTEMPTS;
+    if excess < 0 {
+        return None;
+    }
+    let delay = PASSWORD_BACKOFF_BASE_SECS
+        .saturating_mul(1i64 << excess.min(16))
+        .min(MAX_PASSWORD_BACKOFF_SECS);
+    let last_failure = link.last_password_failure_at?;
+    let remaining = (last_failure + Duration::seconds(delay) - now).num_seconds();
+    (remaining > 0).then_some(remaining)
+}
+
+fn link_status(link: &DocumentShareLinkRecord, now: DateTime<Utc>) -> &'static str {
+    if link.revoked_at.is_some() {
+        "revoked"
+    } else if link.expires_at <= now {
+        "expired"
+    } else {
+        "active"
+    }
+}
+
+fn share_link_to_info(link: DocumentShareLinkRecord) -> ShareLinkInfo {
+    ShareLinkInfo {
+        status: link_status(&link, Utc::now()),
+        id: link.id.to_string(),
+        path: link.path,
+        matter_id: link.matter_id,
+        recipient: link.recipient,
+        password_protected: link.password_hash.is_some(),
+        watermark: link.watermark,
+        created_by: link.created_by,
+        expires_at: link.expires_at.to_rfc3339(),
+        revoked_at: link.revoked_at.map(|ts| ts.to_rfc3339()),
+        download_count: link.download_count,
+        last_downloaded_at: link.last_downloaded_at.map(|ts| ts.to_rfc3339()),
+        failed_password_attempts: link.failed_password_attempts,
+        created_at: link.created_at.to_rfc3339(),
+    }
+}
+
+/// Stamp a shared copy with who it was shared with and when it was fetched,
+/// so a forwarded copy can be traced back to its link.
+pub(crate) fn watermark_shared_document(
+    content: &str,
+    link: &DocumentShareLinkRecord,
+    downloaded_at: DateTime<Utc>,
+) -> String {
+    let recipient = link
+        .recipient
+        .as_deref()
+        .map(|r| format!(" for {r}"))
+        .unwrap_or_default();
+    let stamp = format!(
+        "CONFIDENTIAL - shared{recipient} via link {} - downloaded {} - not for further distribution",
+        link.id,
+        downloaded_at.format("%Y-%m-%d %H:%M UTC"),
+    );
+    format!("> {stamp}\n\n{}\n\n> {stamp}\n", content.trim_end())
+}
+
+/// `POST /api/memory/share` — create a share link for one document.
+/// Requires Collaborator on the document's matter (owner for non-matter
+/// documents).
+pub(crate) async fn share_link_create_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Json(req): Json<CreateShareLinkRequest>,
+) -> Result<(StatusCode, Json<CreateShareLinkResponse>), (StatusCode, String)> {
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let store = require_store(state.as_ref())?;
+    let path = require_document_access(
+        state.as_ref(),
+        &principal.user_id,
+        &req.path,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let hours = req.expires_in_hours.unwrap_or(DEFAULT_EXPIRY_HOURS);
+    if !(1..=MAX_EXPIRY_HOURS).contains(&hours) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("'expires_in_hours' must be between 1 and {MAX_EXPIRY_HOURS}"),
+        ));
+    }
+    let password = req.password.as_deref().filter(|p| !p.is_empty());
+    if password.is_some_and(|p| p.chars().count() < MIN_PASSWORD_LEN) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("'password' must be at least {MIN_PASSWORD_LEN} characters"),
+        ));
+    }
+    let recipient = req
+        .recipient
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .map(str::to_string);
+    if recipient
+        .as_deref()
+        .is_some_and(|r| r.chars().count() > MAX_RECIPIENT_LEN)
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("'recipient' must be at most {MAX_RECIPIENT_LEN} characters"),
+        ));
+    }
+    match workspace.read(&path).await {
+        Ok(_) => {}
+        Err(crate::error::WorkspaceError::DocumentNotFound { .. }) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                format!("Document '{path}' not found"),
+            ));
+        }
+        Err(err) => return Err((StatusCode::INTERNAL_SERVER_ERROR, err.to_string())),
+    }
+
+    let password_hash = match password {
+        Some(password) => {
+            let password = password.to_string();
+            Some(blocking_password_op(move || hash_share_password(&password)).await?)
+        }
+        None => None,
+    };
+
+    let token = generate_share_token();
+    let matter_id = matter_for_path(state.as_ref(), &path);
+    let link = store
+        .create_document_share_link(&NewDocumentShareLinkParams {
+            user_id: state.user_id.clone(),
+            path,
+            matter_id: matter_id.clone(),
+            token_hash: hash_auth_token(&token),
+            password_hash,
+            recipient,
+            watermark: req.watermark.unwrap_or(true),
+            created_by: principal.user_id.clone(),
+            expires_at: Utc::now() + Duration::hours(hours),
+        })
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "document_share_created",
+        &principal.user_id,
+        matter_id.as_deref(),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "share_id": link.id.to_string(),
+            "path": link.path,
+            "recipient": link.recipient,
+            "expires_at": link.expires_at.to_rfc3339(),
+            "password_protected": link.password_hash.is_some(),
+        }),
+    )
+    .await;
+
+    let url = format!("/api/share/{token}");
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateShareLinkResponse {
+            link: share_link_to_info(link),
+            token,
+            url,
+        }),
+    ))
+}
+
+/// `GET /api/memory/share?path=` — share links, newest first. Members only
+/// see links on matters they can view.
+pub(crate) async fn share_links_list_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Query(query): Query<ShareLinksQuery>,
+) -> Result<Json<ShareLinksResponse>, (StatusCode, String)> {
+    let store = require_store(state.as_ref())?;
+    let path = match query.path.as_deref().filter(|p| !p.trim().is_empty()) {
+        Some(raw) => Some(
+            require_document_access(
+                state.as_ref(),
+                &principal.user_id,
+                raw,
+                MatterMemberRole::Viewer,
+            )
+            .await?,
+        ),
+        None => None,
+    };
+    let links = store
+        .list_document_share_links(&state.user_id, path.as_deref())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let mut visible = Vec::with_capacity(links.len());
+    for link in links {
+        if path.is_none()
+            && principal.user_id != state.user_id
+            && require_document_access(
+                state.as_ref(),
+                &principal.user_id,
+                &link.path,
+                MatterMemberRole::Viewer,
+            )
+            .await
+            .is_err()
+        {
+            continue;
+        }
+        visible.push(share_link_to_info(link));
+    }
+    Ok(Json(ShareLinksResponse { links: visible }))
+}
+
+/// `DELETE /api/memory/share/{id}` — revoke a link. Requires the same access
+/// as creating one.
+pub(crate) async fn share_link_revoke_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<ShareLinkInfo>, (StatusCode, String)> {
+    let store = require_store(state.as_ref())?;
+    let not_found = || (StatusCode::NOT_FOUND, "Share link not found".to_string());
+    let id = Uuid::parse_str(&id).map_err(|_| not_found())?;
+    let link = store
+        .get_document_share_link(&state.user_id, id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(not_found)?;
+    require_document_access(
+        state.as_ref(),
+        &principal.user_id,
+        &link.path,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let link = store
+        .revoke_document_share_link(&state.user_id, id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(not_found)?;
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "document_share_revoked",
+        &principal.user_id,
+        link.matter_id.as_deref(),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "share_id": link.id.to_string(),
+            "path": link.path,
+        }),
+    )
+    .await;
+    Ok(Json(share_link_to_info(link)))
+}
+
+/// `GET /api/share/{token}` — download a shared document (unauthenticated).
+/// Password-protected links answer 401 here; use `POST` with the password.
+pub(crate) async fn share_download_handler(
+    State(state): State<Arc<GatewayState>>,
+    Path(token): Path<String>,
+) -> Result<Response, (StatusCode, String)> {
+    serve_share_download(state.as_ref(), &token, None).await
+}
+
+/// `POST /api/share/{token}` — download a password-protected document.
+pub(crate) async fn share_download_with_password_handler(
+    State(state): State<Arc<GatewayState>>,
+    Path(token): Path<String>,
+    Json(req): Json<ShareDownloadRequest>,
+) -> Result<Response, (StatusCode, String)> {
+    serve_share_download(state.as_ref(), &token, req.password.as_deref()).await
+}
+
+async fn serve_share_download(
+    state: &GatewayState,
+    token: &str,
+    password: Option<&str>,
+) -> Result<Response, (StatusCode, String)> {
+    // Unknown, expired, and revoked links all look the same to the caller.
+    let not_found = || (StatusCode::NOT_FOUND, "Share link not found".to_string());
+    let store = require_store(state)?;
+    let workspace = state.workspace.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace not available".to_string(),
+    ))?;
+    let now = Utc::now();
+    let link = store
+        .get_document_share_link_by_token_hash(&hash_auth_token(token))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .filter(|link| link.user_id == state.user_id && link.is_active(now))
+        .ok_or_else(not_found)?;
+
+    if let Some(stored) = link.password_hash.clone() {
+        let Some(password) = password else {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                "This link requires a password".to_string(),
+            ));
+        };
+        let too_many = |wait: i64| {
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                format!("Too many wrong passwords; try again in {wait} seconds"),
+            )
+        };
+        if let Some(wait) = password_retry_after(&link, now) {
+            return Err(too_many(wait));
+        }
+        // Count the guess before hashing it, so concurrent guesses cannot
+        // all pass the backoff check; a download clears the count.
+        let claimed = store
+            .claim_document_share_password_attempt(link.id, link.failed_password_attempts)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or_else(|| too_many(1))?;
+        let password = password.to_string();
+        if !blocking_password_op(move || verify_share_password(&stored, &password)).await? {
+            audit_password_failure(state, &claimed).await;
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                "This link requires a password".to_string(),
+            ));
+        }
+    }
+
+    let doc = workspace.read(&link.path).await.map_err(|e| match e {
+        crate::error::WorkspaceError::DocumentNotFound { .. } => not_found(),
+        other => (StatusCode::INTERNAL_SERVER_ERROR, other.to_string()),
+    })?;
+    store
+        .record_document_share_download(link.id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    crate::channels::web::server::record_legal_audit_event(
+        state,
+        "document_share_downloaded",
+        "share-link",
+        link.matter_id.as_deref(),
+        AuditSeverity::Info,
+        serde_json::json!({
+            "share_id": link.id.to_string(),
+            "path": link.path,
+            "recipient": link.recipient,
+            "download_number": link.download_count + 1,
+        }),
+    )
+    .await;
+
+    let body = if link.watermark {
+        watermark_shared_document(&doc.content, &link, now)
+    } else {
+        doc.content
+    };
+    let file_name: String = link
+        .path
+        .rsplit('/')
+        .next()
+        .unwrap_or("document.md")
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, '.' | '-' | '_'))
+        .collect();
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                "text/markdown; charset=utf-8".to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{file_name}\""),
+            ),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Audit a rejected password and count it against the link.
+async fn audit_password_failure(state: &GatewayState, link: &DocumentShareLinkRecord) {
+    crate::channels::web::server::record_legal_audit_event(
+        state,
+        "document_share_password_rejected",
+        "share-link",
+        link.matter_id.as_deref(),
+        AuditSeverity::Warn,
+        serde_json::json!({
+            "share_id": link.id.to_string(),
+            "path": link.path,
+            "failed_attempts": link.failed_password_attempts,
+        }),
+    )
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shared_link(now: DateTime<Utc>) -> DocumentShareLinkRecord {
+        DocumentShareLinkRecord {
+            id: Uuid::new_v4(),
+            user_id: "default".to_string(),
+            path: "matters/acme/drafts/brief.md".to_string(),
+            matter_id: Some("acme".to_string()),
+            token_hash: String::new(),
+            password_hash: None,
+            recipient: Some("Co-Counsel LLP".to_string()),
+            watermark: true,
+            created_by: "default".to_string(),
+            expires_at: now + Duration::hours(1),
+            revoked_at: None,
+            download_count: 0,
+            last_downloaded_at: None,
+            failed_password_attempts: 0,
+            last_password_failure_at: None,
+            created_at: now,
+        }
+    }
+
+    #[test]
+    fn pbkdf2_matches_published_vectors() {
+        assert_eq!(
+            to_hex(&pbkdf2_sha256(b"password", b"salt", 1)),
+            "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b"
+        );
+        assert_eq!(
+            to_hex(&pbkdf2_sha256(b"password", b"salt", 4096)),
+            "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a"
+        );
+    }
+
+    #[test]
+    fn share_passwords_are_salted_and_verified() {
+        let first = hash_share_password("correct horse");
+        let second = hash_share_password("correct horse");
+        assert_ne!(first, second, "each hash gets its own salt");
+        assert!(first.starts_with(&format!("pbkdf2-sha256${PBKDF2_ITERATIONS}$")));
+        assert!(verify_share_password(&first, "correct horse"));
+        assert!(!verify_share_password(&first, "wrong horse"));
+        assert!(!verify_share_password("malformed", "correct horse"));
+        // Hashes from the old single-round scheme are not accepted.
+        assert!(!verify_share_password(
+            "00112233445566778899aabbccddeeff$abcdef",
+            "correct horse"
+        ));
+    }
+
+    #[test]
+    fn password_guesses_back_off_without_locking() {
+        let now = Utc::now();
+        let mut link = shared_link(now);
+        link.failed_password_attempts = FREE_PASSWORD_ATTEMPTS - 1;
+        link.last_password_failure_at = Some(now);
+        assert_eq!(password_retry_after(&link, now), None);
+
+        link.failed_password_attempts = FREE_PASSWORD_ATTEMPTS;
+        assert_eq!(
+            password_retry_after(&link, now),
+            Some(PASSWORD_BACKOFF_BASE_SECS)
+        );
+        link.failed_password_attempts = FREE_PASSWORD_ATTEMPTS + 1;
+        assert_eq!(
+            password_retry_after(&link, now),
+            Some(2 * PASSWORD_BACKOFF_BASE_SECS)
+        );
+        link.failed_password_attempts = 1_000;
+        assert_eq!(
+            password_retry_after(&link, now),
+            Some(MAX_PASSWORD_BACKOFF_SECS)
+        );
+        assert_eq!(
+            password_retry_after(&link, now + Duration::seconds(MAX_PASSWORD_BACKOFF_SECS)),
+            None,
+            "guessing resumes once the delay has passed"
+        );
+        assert_eq!(link_status(&link, now), "active");
+    }
+
+    #[test]
+    fn watermark_wraps_content_with_recipient_stamp() {
+        let now = Utc::now();
+        let link = shared_link(now);
+        let stamped = watermark_shared_document("# Brief\n\nArgument.\n", &link, now);
+        assert!(stamped.starts_with("> CONFIDENTIAL - shared for Co-Counsel LLP via link"));
+        assert!(stamped.contains("# Brief\n\nArgument."));
+        assert_eq!(stamped.matches(&link.id.to_string()).count(), 2);
+    }
+}
@@ -0,0 +1,683 @@
+//! Firm-wide shared template library handlers.
+//!
+//! Shared templates are document templates with no matter. They carry
+//! practice-area and jurisdiction categories, keep a version per change, and
+//! can be promoted from a matter template. Everyone can read the library;
+//! only the workspace owner can change it.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+use uuid::Uuid;
+
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::handlers::matters::documents::template_for_principal;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{
+    Database, DocumentTemplateRecord, MatterMemberRole, SetTemplateLibraryEntryParams,
+    TemplateLibraryEntryRecord, UpdateDocumentTemplateParams, UpsertDocumentTemplateParams,
+};
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new()
+        .route(
+            "/api/templates",
+            get(shared_templates_handler).post(shared_templates_create_handler),
+        )
+        .route("/api/templates/analytics", get(template_analytics_handler))
+        .route(
+            "/api/templates/{id}",
+            get(shared_template_detail_handler)
+                .patch(shared_template_update_handler)
+                .delete(shared_template_delete_handler),
+        )
+        .route(
+            "/api/templates/{id}/versions",
+            get(shared_template_versions_handler),
+        )
+        .route(
+            "/api/templates/{id}/versions/{version}/restore",
+            post(shared_template_restore_handler),
+        )
+        .route(
+            "/api/templates/{id}/promote",
+            post(template_promote_handler),
+        )
+}
+
+fn store_or_503(state: &GatewayState) -> Result<&Arc<dyn Database>, (StatusCode, String)> {
+    state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))
+}
+
+fn db_error(err: crate::error::DatabaseError) -> (StatusCode, String) {
+    (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+}
+
+fn require_library_owner(
+    state: &GatewayState,
+    principal_user_id: &str,
+) -> Result<(), (StatusCode, String)> {
+    if principal_user_id != state.user_id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only the workspace owner can manage the template library".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Load a shared template; matter templates are reported as not found.
+async fn shared_template(
+    state: &GatewayState,
+    principal_user_id: &str,
+    raw_id: &str,
+    role: MatterMemberRole,
+) -> Result<DocumentTemplateRecord, (StatusCode, String)> {
+    let template = template_for_principal(state, principal_user_id, raw_id, role).await?;
+    if template.matter_id.is_some() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            "Shared template not found".to_string(),
+        ));
+    }
+    Ok(template)
+}
+
+async fn library_entry(
+    store: &Arc<dyn Database>,
+    user_id: &str,
+    template_id: Uuid,
+) -> Result<Option<TemplateLibraryEntryRecord>, (StatusCode, String)> {
+    Ok(store
+        .list_template_library_entries(user_id)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .find(|entry| entry.template_id == template_id))
+}
+
+/// Reject a shared name already used by a different shared template.
+async fn ensure_shared_name_free(
+    store: &Arc<dyn Database>,
+    user_id: &str,
+    name: &str,
+    except: Option<Uuid>,
+) -> Result<(), (StatusCode, String)> {
+    let existing = store
+        .get_document_template_by_name(user_id, None, name)
+        .await
+        .map_err(db_error)?;
+    match existing {
+        Some(existing) if Some(existing.id) != except => Err((
+            StatusCode::CONFLICT,
+            format!("A shared template named '{name}' already exists"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn parse_template_name(raw: &str) -> Result<String, (StatusCode, String)> {
+    let name = crate::channels::web::server::parse_required_matter_field("name", raw)?;
+    crate::channels::web::server::validate_optional_matter_field_length(
+        "name",
+        &Some(name.clone()),
+    )?;
+    Ok(name)
+}
+
+fn parse_template_body(raw: &str) -> Result<String, (StatusCode, String)> {
+    if raw.trim().is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "'body' is required".to_string()));
+    }
+    Ok(raw.to_string())
+}
+
+fn parse_template_variables(
+    raw: &serde_json::Value,
+) -> Result<serde_json::Value, (StatusCode, String)> {
+    let variables = crate::legal::docgen::parse_variable_schema(raw)
+        .map_err(|err| (StatusCode::BAD_REQUEST, err))?;
+    serde_json::to_value(&variables)
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))
+}
+
+fn parse_category(
+    field: &str,
+    raw: Option<String>,
+) -> Result<Option<String>, (StatusCode, String)> {
+    let value = crate::channels::web::server::parse_optional_matter_field(raw);
+    crate::channels::web::server::validate_optional_matter_field_length(field, &value)?;
+    Ok(value)
+}
+
+/// Apply a category patch: `None` keeps the current value, `""` clears it.
+fn patch_category(
+    field: &str,
+    raw: Option<String>,
+    current: Option<String>,
+) -> Result<Option<String>, (StatusCode, String)> {
+    match raw {
+        None => Ok(current),
+        Some(raw) => parse_category(field, Some(raw)),
+    }
+}
+
+fn matches_category(filter: Option<&str>, value: Option<&str>) -> bool {
+    match filter.map(str::trim).filter(|filter| !filter.is_empty()) {
+        None => true,
+        Some(filter) => value.is_some_and(|value| value.eq_ignore_ascii_case(filter)),
+    }
+}
+
+fn shared_template_info(
+    template: &DocumentTemplateRecord,
+    entry: Option<&TemplateLibraryEntryRecord>,
+) -> SharedTemplateInfo {
+    SharedTemplateInfo {
+        id: template.id.to_string(),
+        name: template.name.clone(),
+        practice_area: entry.and_then(|entry| entry.practice_area.clone()),
+        jurisdiction: entry.and_then(|entry| entry.jurisdiction.clone()),
+        description: entry.and_then(|entry| entry.description.clone()),
+        promoted_from: entry.and_then(|entry| entry.promoted_from.map(|id| id.to_string())),
+        variables: template.variables_json.clone(),
+        updated_at: template.updated_at.to_rfc3339(),
+    }
+}
+
+async fn shared_template_detail(
+    store: &Arc<dyn Database>,
+    user_id: &str,
+    template: DocumentTemplateRecord,
+) -> Result<SharedTemplateDetailResponse, (StatusCode, String)> {
+    let entry = library_entry(store, user_id, template.id).await?;
+    let version = store
+        .list_document_template_versions(user_id, template.id)
+        .await
+        .map_err(db_error)?
+        .first()
+        .map_or(0, |latest| latest.version);
+    Ok(SharedTemplateDetailResponse {
+        template: shared_template_info(&template, entry.as_ref()),
+        body: template.body,
+        version,
+    })
+}
+
+/// `GET /api/templates` — shared templates, optionally filtered by
+/// `practice_area` and `jurisdiction` (case-insensitive).
+pub(crate) async fn shared_templates_handler(
+    State(state): State<Arc<GatewayState>>,
+    Query(query): Query<SharedTemplatesQuery>,
+) -> Result<Json<SharedTemplatesResponse>, (StatusCode, String)> {
+    let store = store_or_503(state.as_ref())?;
+    let entries: HashMap<Uuid, TemplateLibraryEntryRecord> = store
+        .list_template_library_entries(&state.user_id)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|entry| (entry.template_id, entry))
+        .collect();
+    let templates = store
+        .list_document_templates(&state.user_id, None)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .filter(|template| template.matter_id.is_none())
+        .filter_map(|template| {
+            let entry = entries.get(&template.id);
+            let practice_area = entry.and_then(|entry| entry.practice_area.as_deref());
+            let jurisdiction = entry.and_then(|entry| entry.jurisdiction.as_deref());
+            (matches_category(query.practice_area.as_deref(), practice_area)
+                && matches_category(query.jurisdiction.as_deref(), jurisdiction))
+            .then(|| shared_template_info(&template, entry))
+        })
+        .collect();
+    Ok(Json(SharedTemplatesResponse { templates }))
+}
+
+/// `POST /api/templates` — add a template to the shared library.
+pub(crate) async fn shared_templates_create_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Json(req): Json<CreateSharedTemplateRequest>,
+) -> Result<(StatusCode, Json<SharedTemplateDetailResponse>), (StatusCode, String)> {
+    require_library_owner(state.as_ref(), &principal.user_id)?;
+    let store = store_or_503(state.as_ref())?;
+    let name = parse_template_name(&req.name)?;
+    let body = parse_template_body(&req.body)?;
+    let variables_json = match req.variables {
+        Some(ref raw) => parse_template_variables(raw)?,
+        None => serde_json::json!([]),
+    };
+    let entry = SetTemplateLibraryEntryParams {
+        practice_area: parse_category("practice_area", req.practice_area)?,
+        jurisdiction: parse_category("jurisdiction", req.jurisdiction)?,
+        description: parse_category("description", req.description)?,
+        promoted_from: None,
+    };
+    ensure_shared_name_free(store, &state.user_id, &name, None).await?;
+
+    let template = store
+        .upsert_document_template(
+            &state.user_id,
+            &UpsertDocumentTemplateParams {
+                matter_id: None,
+                name,
+                body,
+                variables_json,
+            },
+        )
+        .await
+        .map_err(db_error)?;
+    store
+        .set_template_library_entry(&state.user_id, template.id, &entry)
+        .await
+        .map_err(db_error)?;
+    store
+        .record_document_template_version(&state.user_id, template.id, &principal.user_id)
+        .await
+        .map_err(db_error)?;
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "shared_template_created",
+        &principal.user_id,
+        None,
+        crate::db::AuditSeverity::Info,
+        serde_json::json!({
+            "template_id": template.id,
+            "name": template.name,
+            "practice_area": entry.practice_area,
+            "jurisdiction": entry.jurisdiction,
+        }),
+    )
+    .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(shared_template_detail(store, &state.user_id, template).await?),
+    ))
+}
+
+/// `GET /api/templates/{id}` — one shared template with its body.
+pub(crate) async fn shared_template_detail_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<SharedTemplateDetailResponse>, (StatusCode, String)> {
+    let template = shared_template(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Viewer,
+    )
+    .await?;
+    let store = store_or_503(state.as_ref())?;
+    Ok(Json(
+        shared_template_detail(store, &state.user_id, template).await?,
+    ))
+}
+
+/// `PATCH /api/templates/{id}` — edit a shared template; content changes
+/// record a new version.
+pub(crate) async fn shared_template_update_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateSharedTemplateRequest>,
+) -> Result<Json<SharedTemplateDetailResponse>, (StatusCode, String)> {
+    let template = shared_template(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let store = store_or_503(state.as_ref())?;
+    let name = req.name.as_deref().map(parse_template_name).transpose()?;
+    let body = req.body.as_deref().map(parse_template_body).transpose()?;
+    let variables_json = req
+        .variables
+        .as_ref()
+        .map(parse_template_variables)
+        .transpose()?;
+    let current = library_entry(store, &state.user_id, template.id)
+        .await?
+        .unwrap_or(TemplateLibraryEntryRecord {
+            template_id: template.id,
+            user_id: state.user_id.clone(),
+            practice_area: None,
+            jurisdiction: None,
+            description: None,
+            promoted_from: None,
+            updated_at: template.updated_at,
+        });
+    let entry = SetTemplateLibraryEntryParams {
+        practice_area: patch_category("practice_area", req.practice_area, current.practice_area)?,
+        jurisdiction: patch_category("jurisdiction", req.jurisdiction, current.jurisdiction)?,
+        description: patch_category("description", req.description, current.description)?,
+        promoted_from: current.promoted_from,
+    };
+    if let Some(ref name) = name {
+        ensure_shared_name_free(store, &state.user_id, name, Some(template.id)).await?;
+    }
+
+    let updated = store
+        .update_document_template(
+            &state.user_id,
+            template.id,
+            &UpdateDocumentTemplateParams {
+                name,
+                body,
+                variables_json,
+            },
+        )
+        .await
+        .map_err(db_error)?
+        .ok_or((StatusCode::NOT_FOUND, "Template not found".to_string()))?;
+    store
+        .set_template_library_entry(&state.user_id, updated.id, &entry)
+        .await
+        .map_err(db_error)?;
+    let version = store
+        .record_document_template_version(&state.user_id, updated.id, &principal.user_id)
+        .await
+        .map_err(db_error)?;
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "shared_template_updated",
+        &principal.user_id,
+        None,
+        crate::db::AuditSeverity::Info,
+        serde_json::json!({
+            "template_id": updated.id,
+            "name": updated.name,
+            "version": version.map(|version| version.version),
+        }),
+    )
+    .await;
+
+    Ok(Json(
+        shared_template_detail(store, &state.user_id, updated).await?,
+    ))
+}
+
+/// `DELETE /api/templates/{id}` — remove a shared template with its versions
+/// and usage history.
+pub(crate) async fn shared_template_delete_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let template = shared_template(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let store = store_or_503(state.as_ref())?;
+    let deleted = store
+        .delete_document_template(&state.user_id, template.id)
+        .await
+        .map_err(db_error)?;
+    if !deleted {
+        return Err((StatusCode::NOT_FOUND, "Template not found".to_string()));
+    }
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "shared_template_deleted",
+        &principal.user_id,
+        None,
+        crate::db::AuditSeverity::Warn,
+        serde_json::json!({
+            "template_id": template.id,
+            "name": template.name,
+        }),
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /api/templates/{id}/versions` — version history, newest first.
+pub(crate) async fn shared_template_versions_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<Json<TemplateVersionsResponse>, (StatusCode, String)> {
+    let template = shared_template(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Viewer,
+    )
+    .await?;
+    let store = store_or_503(state.as_ref())?;
+    let versions = store
+        .list_document_template_versions(&state.user_id, template.id)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|version| TemplateVersionInfo {
+            version: version.version,
+            name: version.name,
+            body: version.body,
+            variables: version.variables_json,
+            created_by: version.created_by,
+            created_at: version.created_at.to_rfc3339(),
+        })
+        .collect();
+    Ok(Json(TemplateVersionsResponse {
+        template_id: template.id.to_string(),
+        versions,
+    }))
+}
+
+/// `POST /api/templates/{id}/versions/{version}/restore` — write an earlier
+/// version back as the current content, recorded as a new version.
+pub(crate) async fn shared_template_restore_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path((id, version)): Path<(String, i32)>,
+) -> Result<Json<SharedTemplateDetailResponse>, (StatusCode, String)> {
+    let template = shared_template(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Collaborator,
+    )
+    .await?;
+    let store = store_or_503(state.as_ref())?;
+    let target = store
+        .list_document_template_versions(&state.user_id, template.id)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .find(|candidate| candidate.version == version)
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            "Template version not found".to_string(),
+        ))?;
+    ensure_shared_name_free(store, &state.user_id, &target.name, Some(template.id)).await?;
+
+    let restored = store
+        .update_document_template(
+            &state.user_id,
+            template.id,
+            &UpdateDocumentTemplateParams {
+                name: Some(target.name),
+                body: Some(target.body),
+                variables_json: Some(target.variables_json),
+            },
+        )
+        .await
+        .map_err(db_error)?
+        .ok_or((StatusCode::NOT_FOUND, "Template not found".to_string()))?;
+    let recorded = store
+        .record_document_template_version(&state.user_id, restored.id, &principal.user_id)
+        .await
+        .map_err(db_error)?;
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "shared_template_restored",
+        &principal.user_id,
+        None,
+        crate::db::AuditSeverity::Info,
+        serde_json::json!({
+            "template_id": restored.id,
+            "restored_version": version,
+            "version": recorded.map(|recorded| recorded.version),
+        }),
+    )
+    .await;
+
+    Ok(Json(
+        shared_template_detail(store, &state.user_id, restored).await?,
+    ))
+}
+
+/// `POST /api/templates/{id}/promote` — copy a matter template into the
+/// shared library.
+pub(crate) async fn template_promote_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+    Json(req): Json<PromoteTemplateRequest>,
+) -> Result<(StatusCode, Json<SharedTemplateDetailResponse>), (StatusCode, String)> {
+    require_library_owner(state.as_ref(), &principal.user_id)?;
+    let source = template_for_principal(
+        state.as_ref(),
+        &principal.user_id,
+        &id,
+        MatterMemberRole::Viewer,
+    )
+    .await?;
+    let Some(source_matter) = source.matter_id.clone() else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Template is already in the shared library".to_string(),
+        ));
+    };
+    let store = store_or_503(state.as_ref())?;
+    let name = match req.name {
+        Some(ref raw) => parse_template_name(raw)?,
+        None => source.name.clone(),
+    };
+    let entry = SetTemplateLibraryEntryParams {
+        practice_area: parse_category("practice_area", req.practice_area)?,
+        jurisdiction: parse_category("jurisdiction", req.jurisdiction)?,
+        description: parse_category("description", req.description)?,
+        promoted_from: Some(source.id),
+    };
+    ensure_shared_name_free(store, &state.user_id, &name, None).await?;
+
+    let template = store
+        .upsert_document_template(
+            &state.user_id,
+            &UpsertDocumentTemplateParams {
+                matter_id: None,
+                name,
+                body: source.body.clone(),
+                variables_json: source.variables_json.clone(),
+            },
+        )
+        .await
+        .map_err(db_error)?;
+    store
+        .set_template_library_entry(&state.user_id, template.id, &entry)
+        .await
+        .map_err(db_error)?;
+    store
+        .record_document_template_version(&state.user_id, template.id, &principal.user_id)
+        .await
+        .map_err(db_error)?;
+
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "template_promoted",
+        &principal.user_id,
+        Some(source_matter.as_str()),
+        crate::db::AuditSeverity::Info,
+        serde_json::json!({
+            "source_template_id": source.id,
+            "template_id": template.id,
+            "name": template.name,
+            "practice_area": entry.practice_area,
+            "jurisdiction": entry.jurisdiction,
+        }),
+    )
+    .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(shared_template_detail(store, &state.user_id, template).await?),
+    ))
+}
+
+/// `GET /api/templates/analytics` — generated-document counts per template,
+/// most used first.
+pub(crate) async fn template_analytics_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Query(query): Query<TemplateAnalyticsQuery>,
+) -> Result<Json<TemplateAnalyticsResponse>, (StatusCode, String)> {
+    require_library_owner(state.as_ref(), &principal.user_id)?;
+    let store = store_or_503(state.as_ref())?;
+    let since = crate::channels::web::server::parse_optional_datetime("since", query.since)?;
+    let usage = store
+        .document_template_usage(&state.user_id, since)
+        .await
+        .map_err(db_error)?;
+    let templates: HashMap<Uuid, DocumentTemplateRecord> = store
+        .list_document_templates(&state.user_id, None)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|template| (template.id, template))
+        .collect();
+    let entries: HashMap<Uuid, TemplateLibraryEntryRecord> = store
+        .list_template_library_entries(&state.user_id)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|entry| (entry.template_id, entry))
+        .collect();
+
+    let templates: Vec<TemplateUsageInfo> = usage
+        .into_iter()
+        .filter_map(|usage| {
+            let template = templates.get(&usage.template_id)?;
+            let entry = entries.get(&usage.template_id);
+            Some(TemplateUsageInfo {
+                template_id: usage.template_id.to_string(),
+                name: template.name.clone(),
+                matter_id: template.matter_id.clone(),
+                practice_area: entry.and_then(|entry| entry.practice_area.clone()),
+                jurisdiction: entry.and_then(|entry| entry.jurisdiction.clone()),
+                uses: usage.uses,
+                matters: usage.matters,
+                last_used_at: usage.last_used_at.to_rfc3339(),
+            })
+        })
+        .collect();
+    Ok(Json(TemplateAnalyticsResponse {
+        since: since.map(|since| since.to_rfc3339()),
+        total_uses: templates.iter().map(|usage| usage.uses).sum(),
+        templates,
+    }))
+}
@@ -0,0 +1,339 @@
+//! Adverse-party watchlist handlers and screening hooks.
+//!
+//! The watchlist is firm-wide (stored under the gateway owner). Anyone signed
+//! in can read it and screen names; only admins and attorneys change it.
+//! Matter creation, client creation, and document ingestion call the
+//! `screen_*` helpers here, which alert through the legal audit log and the
+//! notification inbox but never block the operation that triggered them.
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+};
+
+use crate::channels::web::auth::RequestPrincipal;
+use crate::channels::web::state::GatewayState;
+use crate::channels::web::types::*;
+use crate::db::{
+    AuditSeverity, Database, NewNotificationParams, NewWatchlistEntryParams, NotificationKind,
+    UserRole, WatchlistCategory, WatchlistEntryRecord,
+};
+use crate::legal::watchlist::WatchlistMatch;
+
+const MAX_WATCHLIST_NAME_CHARS: usize = 200;
+const MAX_WATCHLIST_REASON_CHARS: usize = 1000;
+const MAX_WATCHLIST_SCREEN_NAMES: usize = 500;
+const MAX_WATCHLIST_SCREEN_TEXT_LEN: usize = 256 * 1024;
+
+pub fn routes() -> Router<Arc<GatewayState>> {
+    Router::new()
+        .route(
+            "/api/legal/watchlist",
+            get(watchlist_list_handler).post(watchlist_create_handler),
+        )
+        .route(
+            "/api/legal/watchlist/screen",
+            post(watchlist_screen_handler),
+        )
+        .route(
+            "/api/legal/watchlist/{id}",
+            delete(watchlist_delete_handler),
+        )
+}
+
+fn watchlist_store(state: &GatewayState) -> Result<&Arc<dyn Database>, (StatusCode, String)> {
+    state.store.as_ref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Database not available".to_string(),
+    ))
+}
+
+fn require_watchlist_editor(role: UserRole) -> Result<(), (StatusCode, String)> {
+    if matches!(role, UserRole::Admin | UserRole::Attorney) {
+        Ok(())
+    } else {
+        Err((
+            StatusCode::FORBIDDEN,
+            "Only admins and attorneys can change the watchlist".to_string(),
+        ))
+    }
+}
+
+fn watchlist_entry_to_info(entry: WatchlistEntryRecord) -> WatchlistEntryInfo {
+    WatchlistEntryInfo {
+        id: entry.id.to_string(),
+        name: entry.name,
+        category: entry.category.as_str(),
+        reason: entry.reason,
+        created_by: entry.created_by,
+        created_at: entry.created_at.to_rfc3339(),
+    }
+}
+
+pub(crate) async fn watchlist_list_handler(
+    State(state): State<Arc<GatewayState>>,
+) -> Result<Json<WatchlistListResponse>, (StatusCode, String)> {
+    let entries = watchlist_store(&state)?
+        .list_watchlist_entries(&state.user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(WatchlistListResponse {
+        entries: entries.into_iter().map(watchlist_entry_to_info).collect(),
+    }))
+}
+
+pub(crate) async fn watchlist_create_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Json(req): Json<CreateWatchlistEntryRequest>,
+) -> Result<(StatusCode, Json<WatchlistEntryInfo>), (StatusCode, String)> {
+    require_watchlist_editor(principal.role)?;
+    let store = watchlist_store(&state)?;
+    let name = req.name.trim().to_string();
+    if crate::db::normalize_party_name(&name).is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "'name' must include letters or digits".to_string(),
+        ));
+    }
+    if name.chars().count() > MAX_WATCHLIST_NAME_CHARS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("'name' must be at most {MAX_WATCHLIST_NAME_CHARS} characters"),
+        ));
+    }
+    let category = match req.category.as_deref().map(str::trim) {
+        None | Some("") => WatchlistCategory::AdverseParty,
+        Some(raw) => WatchlistCategory::from_db_value(raw).ok_or((
+            StatusCode::BAD_REQUEST,
+            format!("Unknown category '{raw}' (expected adverse_party, sanctioned, or other)"),
+        ))?,
+    };
+    let reason = crate::channels::web::server::parse_optional_matter_field(req.reason);
+    if reason
+        .as_ref()
+        .is_some_and(|reason| reason.chars().count() > MAX_WATCHLIST_REASON_CHARS)
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("'reason' must be at most {MAX_WATCHLIST_REASON_CHARS} characters"),
+        ));
+    }
+    let entry = store
+        .upsert_watchlist_entry(&NewWatchlistEntryParams {
+            user_id: state.user_id.clone(),
+            name,
+            category,
+            reason,
+            created_by: principal.user_id.clone(),
+        })
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "watchlist_entry_added",
+        &principal.user_id,
+        None,
+        AuditSeverity::Info,
+        serde_json::json!({
+            "entry_id": entry.id.to_string(),
+            "name": entry.name.clone(),
+            "category": entry.category.as_str(),
+        }),
+    )
+    .await;
+    Ok((StatusCode::CREATED, Json(watchlist_entry_to_info(entry))))
+}
+
+pub(crate) async fn watchlist_delete_handler(
+    State(state): State<Arc<GatewayState>>,
+    RequestPrincipal(principal): RequestPrincipal,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    require_watchlist_editor(principal.role)?;
+    let id = crate::channels::web::server::parse_uuid(&id, "id")?;
+    let deleted = watchlist_store(&state)?
+        .delete_watchlist_entry(&state.user_id, id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !deleted {
+        return Err((
+            StatusCode::NOT_FOUND,
+            "Watchlist entry not found".to_string(),
+        ));
+    }
+    crate::channels::web::server::record_legal_audit_event(
+        state.as_ref(),
+        "watchlist_entry_removed",
+        &principal.user_id,
+        None,
+        AuditSeverity::Info,
+        serde_json::json!({ "entry_id": id.to_string() }),
+    )
+    .await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Ad-hoc screening of names and/or free text. Does not raise alerts.
+pub(crate) async fn watchlist_screen_handler(
+    State(state): State<Arc<GatewayState>>,
+    Json(req): Json<WatchlistScreenRequest>,
+) -> Result<Json<WatchlistScreenResponse>, (StatusCode, String)> {
+    if req.names.len() > MAX_WATCHLIST_SCREEN_NAMES {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("'names' may include at most {MAX_WATCHLIST_SCREEN_NAMES} values"),
+        ));
+    }
+    let text = req.text.as_deref().unwrap_or_default();
+    if text.len() > MAX_WATCHLIST_SCREEN_TEXT_LEN {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("'text' must be at most {MAX_WATCHLIST_SCREEN_TEXT_LEN} bytes"),
+        ));
+    }
+    if req.names.iter().all(|name| name.trim().is_empty()) && text.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Provide 'names' or 'text' to screen".to_string(),
+        ));
+    }
+    let entries = watchlist_store(&state)?
+        .list_watchlist_entries(&state.user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let mut matches = crate::legal::watchlist::screen_names(&entries, &req.names);
+    if !text.trim().is_empty() {
+        matches.extend(crate::legal::watchlist::scan_text(&entries, text));
+    }
+    Ok(Json(WatchlistScreenResponse {
+        matched: !matches.is_empty(),
+        matches,
+    }))
+}
+
+async fn load_watchlist(state: &GatewayState) -> Vec<WatchlistEntryRecord> {
+    let Some(store) = state.store.as_ref() else {
+        return Vec::new();
+    };
+    match store.list_watchlist_entries(&state.user_id).await {
+        Ok(entries) => entries,
+        Err(err) => {
+            tracing::warn!("failed to load party watchlist: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// Screen a new client or matter's parties. Best effort: alerts on matches,
+/// never fails the caller.
+pub(crate) async fn screen_parties_against_watchlist(
+    state: &GatewayState,
+    matter_id: Option<&str>,
+    source: &str,
+    names: &[String],
+) -> usize {
+    let entries = load_watchlist(state).await;
+    if entries.is_empty() {
+        return 0;
+    }
+    let matches = crate::legal::watchlist::screen_names(&entries, names);
+    alert_watchlist_matches(state, matter_id, source, None, &matches).await;
+    matches.len()
+}
+
+/// Scan an ingested document for watched names. Best effort, like
+/// [`screen_parties_against_watchlist`].
+pub(crate) async fn scan_document_against_watchlist(
+    state: &GatewayState,
+    matter_id: &str,
+    path: &str,
+    content: &str,
+) -> usize {
+    let entries = load_watchlist(state).await;
+    if entries.is_empty() {
+        return 0;
+    }
+    let matches = crate::legal::watchlist::scan_text(&entries, content);
+    alert_watchlist_matches(
+        state,
+        Some(matter_id),
+        "document_ingested",
+        Some(path),
+        &matches,
+    )
+    .await;
+    matches.len()
+}
+
+async fn alert_watchlist_matches(
+    state: &GatewayState,
+    matter_id: Option<&str>,
+    source: &str,
+    path: Option<&str>,
+    matches: &[WatchlistMatch],
+) {
+    if matches.is_empty() {
+        return;
+    }
+    let sanctioned = matches
+        .iter()
+        .any(|m| m.category == WatchlistCategory::Sanctioned);
+    let severity = if sanctioned {
+        AuditSeverity::Critical
+    } else {
+        AuditSeverity::Warn
+    };
+    crate::channels::web::server::record_legal_audit_event(
+        state,
+        "watchlist_match",
+        &state.user_id,
+        matter_id,
+        severity,
+        serde_json::json!({
+            "source": source,
+            "source_path": path,
+            "matches": matches,
+        }),
+    )
+    .await;
+
+    let Some(store) = state.store.as_ref() else {
+        return;
+    };
+    let mut watched: Vec<&str> = matches.iter().map(|m| m.watched_name.as_str()).collect();
+    watched.dedup();
+    let context = match (path, matter_id) {
+        (Some(path), _) => format!("in {path}"),
+        (None, Some(matter_id)) => format!("on matter {matter_id}"),
+        (None, None) => "on a new client".to_string(),
+    };
+    let body = matches
+        .iter()
+        .map(|m| {
+            let mut line = format!("{} ({})", m.watched_name, m.category.as_str());
+            if let Some(reason) = &m.reason {
+                line.push_str(&format!(": {reason}"));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    crate::notifications::notify(
+        store.as_ref(),
+        NewNotificationParams {
+            user_id: state.user_id.clone(),
+            kind: NotificationKind::System,
+            severity,
+            title: format!("Watchlist match {context}: {}", watched.join(", ")),
+            body,
+            matter_id: matter_id.map(str::to_string),
+            source: Some(format!("watchlist:{source}")),
+        },
+    )
+    .await;
+}
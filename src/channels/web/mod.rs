@@ -18,6 +18,9 @@ pub mod auth;
 pub(crate) mod handlers;
 pub mod log_layer;
 pub mod openai_compat;
+pub mod rate_limit;
+#[cfg(all(test, feature = "libsql"))]
+mod route_auth_tests;
 pub mod server;
 pub mod sse;
 pub mod state;
@@ -30,11 +33,12 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use tokio::sync::mpsc;
-use tokio_stream::wrappers::ReceiverStream;
 
 use crate::agent::SessionManager;
-use crate::channels::{Channel, IncomingMessage, MessageStream, OutgoingResponse, StatusUpdate};
+use crate::channels::{
+    Channel, IncomingMessage, IntakeConfig, IntakeQueue, MessageStream, OutgoingResponse,
+    StatusUpdate,
+};
 use crate::config::{GatewayConfig, LegalConfig};
 use crate::db::Database;
 use crate::error::ChannelError;
@@ -47,8 +51,9 @@ use crate::workspace::Workspace;
 
 use self::log_layer::{LogBroadcaster, LogLevelHandle};
 
+use self::rate_limit::KeyedRateLimiter;
 use self::sse::SseManager;
-use self::state::{GatewayState, RateLimiter};
+use self::state::GatewayState;
 use self::types::SseEvent;
 
 /// Web gateway channel implementing the Channel trait.
@@ -76,6 +81,10 @@ impl GatewayChannel {
 
         let state = Arc::new(GatewayState {
             msg_tx: tokio::sync::RwLock::new(None),
+            intake: Arc::new(IntakeQueue::new(IntakeConfig {
+                capacity: config.intake_capacity,
+                per_user_in_flight: config.intake_per_user_limit,
+            })),
             sse: SseManager::new(),
             workspace: None,
             session_manager: None,
@@ -92,12 +101,18 @@ impl GatewayChannel {
             llm_provider: None,
             skill_registry: None,
             skill_catalog: None,
-            chat_rate_limiter: RateLimiter::new(30, 60),
+            rate_limiter: Arc::new(KeyedRateLimiter::new(config.rate_limits)),
             registry_entries: Vec::new(),
             cost_guard: None,
+            review_queue: None,
+            routine_engine: None,
+            webhooks: None,
+            git_mirror: None,
+            events: crate::events::EventBus::new(),
             startup_time: std::time::Instant::now(),
             legal_config: None,
             runtime_facts: crate::compliance::ComplianceRuntimeFacts::default(),
+            review_token_key: auth::derive_review_token_key(&auth_token),
         });
 
         Self {
@@ -111,6 +126,7 @@ impl GatewayChannel {
     fn rebuild_state(&mut self, mutate: impl FnOnce(&mut GatewayState)) {
         let mut new_state = GatewayState {
             msg_tx: tokio::sync::RwLock::new(None),
+            intake: Arc::clone(&self.state.intake),
             sse: SseManager::new(),
             workspace: self.state.workspace.clone(),
             session_manager: self.state.session_manager.clone(),
@@ -127,12 +143,18 @@ impl GatewayChannel {
             llm_provider: self.state.llm_provider.clone(),
             skill_registry: self.state.skill_registry.clone(),
             skill_catalog: self.state.skill_catalog.clone(),
-            chat_rate_limiter: RateLimiter::new(30, 60),
+            rate_limiter: Arc::clone(&self.state.rate_limiter),
             registry_entries: self.state.registry_entries.clone(),
             cost_guard: self.state.cost_guard.clone(),
+            review_queue: self.state.review_queue.clone(),
+            routine_engine: self.state.routine_engine.clone(),
+            webhooks: self.state.webhooks.clone(),
+            git_mirror: self.state.git_mirror.clone(),
+            events: self.state.events.clone(),
             startup_time: self.state.startup_time,
             legal_config: self.state.legal_config.clone(),
             runtime_facts: self.state.runtime_facts.clone(),
+            review_token_key: self.state.review_token_key.clone(),
         };
         mutate(&mut new_state);
         self.state = Arc::new(new_state);
@@ -232,6 +254,30 @@ impl GatewayChannel {
         self
     }
 
+    /// Inject the supervised-mode review queue for the draft review API.
+    pub fn with_review_queue(mut self, queue: Arc<crate::channels::ReviewQueue>) -> Self {
+        self.rebuild_state(|s| s.review_queue = Some(queue));
+        self
+    }
+
+    /// Inject the routine engine handle for inbound routine webhooks.
+    pub fn with_routine_engine(mut self, slot: crate::agent::RoutineEngineSlot) -> Self {
+        self.rebuild_state(|s| s.routine_engine = Some(slot));
+        self
+    }
+
+    /// Inject the outbound webhook dispatcher for the integrations API.
+    pub fn with_webhooks(mut self, webhooks: Arc<crate::integrations::WebhookDispatcher>) -> Self {
+        self.rebuild_state(|s| s.webhooks = Some(webhooks));
+        self
+    }
+
+    /// Inject the workspace git mirror for the mirror API.
+    pub fn with_git_mirror(mut self, mirror: Arc<crate::workspace::git_mirror::GitMirror>) -> Self {
+        self.rebuild_state(|s| s.git_mirror = Some(mirror));
+        self
+    }
+
     /// Inject legal config for web legal-policy endpoints.
     pub fn with_legal_config(mut self, legal_config: LegalConfig) -> Self {
         self.rebuild_state(|s| s.legal_config = Some(legal_config));
@@ -262,7 +308,7 @@ impl Channel for GatewayChannel {
     }
 
     async fn start(&self) -> Result<MessageStream, ChannelError> {
-        let (tx, rx) = mpsc::channel(256);
+        let (tx, stream) = self.state.intake.channel();
         *self.state.msg_tx.write().await = Some(tx);
 
         let addr: SocketAddr = format!("{}:{}", self.config.host, self.config.port)
@@ -276,8 +322,9 @@ impl Channel for GatewayChannel {
             })?;
 
         server::start_server(addr, self.state.clone(), self.auth_token.clone()).await?;
+        self.state.spawn_event_subscribers();
 
-        Ok(Box::pin(ReceiverStream::new(rx)))
+        Ok(stream)
     }
 
     async fn respond(
@@ -286,10 +333,13 @@ impl Channel for GatewayChannel {
         response: OutgoingResponse,
     ) -> Result<(), ChannelError> {
         let thread_id = msg.thread_id.clone().unwrap_or_default();
+        let citations =
+            handlers::memory::resolve_chunk_citations(&self.state, &response.content).await;
 
         self.state.sse.broadcast(SseEvent::Response {
             content: response.content,
             thread_id,
+            citations,
         });
 
         Ok(())
@@ -384,9 +434,12 @@ impl Channel for GatewayChannel {
         _user_id: &str,
         response: OutgoingResponse,
     ) -> Result<(), ChannelError> {
+        let citations =
+            handlers::memory::resolve_chunk_citations(&self.state, &response.content).await;
         self.state.sse.broadcast(SseEvent::Response {
             content: response.content,
             thread_id: String::new(),
+            citations,
         });
         Ok(())
     }
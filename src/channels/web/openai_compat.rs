@@ -15,11 +15,12 @@ use axum::{
         sse::{Event, KeepAlive, Sse},
     },
 };
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 
 use crate::llm::{
-    ChatMessage, CompletionRequest, FinishReason, Role, ToolCall, ToolCompletionRequest,
-    ToolDefinition,
+    ChatMessage, CompletionRequest, FinishReason, Role, StreamChunk, ToolCall,
+    ToolCompletionRequest, ToolDefinition,
 };
 
 use super::state::GatewayState;
@@ -46,6 +47,15 @@ pub struct OpenAiChatRequest {
     pub tool_choice: Option<serde_json::Value>,
     #[serde(default)]
     pub stop: Option<serde_json::Value>,
+    #[serde(default)]
+    pub stream_options: Option<OpenAiStreamOptions>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct OpenAiStreamOptions {
+    /// Emit a final chunk with `usage` and empty `choices` before `[DONE]`.
+    #[serde(default)]
+    pub include_usage: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -130,6 +140,8 @@ pub struct OpenAiChatChunk {
     pub created: u64,
     pub model: String,
     pub choices: Vec<OpenAiChunkChoice>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<OpenAiUsage>,
 }
 
 #[derive(Debug, Serialize)]
@@ -140,7 +152,7 @@ pub struct OpenAiChunkChoice {
     pub finish_reason: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize)]
 pub struct OpenAiDelta {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub role: Option<String>,
@@ -353,7 +365,7 @@ fn map_llm_error(err: crate::error::LlmError) -> (StatusCode, Json<OpenAiErrorRe
     )
 }
 
-fn openai_error(
+pub(crate) fn openai_error(
     status: StatusCode,
     message: impl Into<String>,
     error_type: &str,
@@ -426,14 +438,6 @@ pub async fn chat_completions_handler(
     State(state): State<Arc<GatewayState>>,
     Json(req): Json<OpenAiChatRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<OpenAiErrorResponse>)> {
-    if !state.chat_rate_limiter.check() {
-        return Err(openai_error(
-            StatusCode::TOO_MANY_REQUESTS,
-            "Rate limit exceeded. Please try again later.",
-            "rate_limit_error",
-        ));
-    }
-
     let llm = state.llm_provider.as_ref().ok_or_else(|| {
         openai_error(
             StatusCode::SERVICE_UNAVAILABLE,
@@ -569,11 +573,10 @@ pub async fn chat_completions_handler(
 
 /// Handle streaming responses.
 ///
-/// The current `LlmProvider` returns complete responses (no streaming method).
-/// We execute the LLM call first, then simulate chunked delivery by splitting
-/// the response into word-boundary chunks. This ensures LLM failures return
-/// proper HTTP errors instead of SSE error events. True token streaming can be
-/// added later by extending `LlmProvider` with a `complete_stream()` method.
+/// Chunks come from `LlmProvider::complete_stream`: providers with native
+/// streaming yield tokens as they are generated, others replay a finished
+/// response. Failures before the first chunk still return proper HTTP errors;
+/// failures mid-stream are sent as an SSE `error` payload before `[DONE]`.
 async fn handle_streaming(
     llm: Arc<dyn crate::llm::LlmProvider>,
     req: OpenAiChatRequest,
@@ -583,222 +586,163 @@ async fn handle_streaming(
         .map_err(|e| openai_error(StatusCode::BAD_REQUEST, e, "invalid_request_error"))?;
 
     let requested_model = req.model.clone();
+    let include_usage = req
+        .stream_options
+        .as_ref()
+        .is_some_and(|opts| opts.include_usage);
     let id = chat_completion_id();
     let created = unix_timestamp();
 
-    // Execute the LLM call before starting the SSE stream.
-    // Since streaming is simulated (LlmProvider returns complete responses),
-    // this lets us return proper HTTP errors on failure.
-    enum LlmResult {
-        Simple(crate::llm::CompletionResponse),
-        WithTools(crate::llm::ToolCompletionResponse),
+    let tools = if has_tools {
+        convert_tools(req.tools.as_deref().unwrap_or(&[]))
+    } else {
+        Vec::new()
+    };
+    let mut stream_req = ToolCompletionRequest::new(messages, tools).with_model(req.model);
+    if let Some(t) = req.temperature {
+        stream_req = stream_req.with_temperature(t);
+    }
+    if let Some(mt) = req.max_tokens {
+        stream_req = stream_req.with_max_tokens(mt);
+    }
+    if has_tools
+        && let Some(ref tc) = req.tool_choice
+        && let Some(choice) = normalize_tool_choice(tc)
+    {
+        stream_req = stream_req.with_tool_choice(choice);
     }
 
-    let llm_result = if has_tools {
-        let tools = convert_tools(req.tools.as_deref().unwrap_or(&[]));
-        let mut tool_req = ToolCompletionRequest::new(messages, tools).with_model(req.model);
-        if let Some(t) = req.temperature {
-            tool_req = tool_req.with_temperature(t);
-        }
-        if let Some(mt) = req.max_tokens {
-            tool_req = tool_req.with_max_tokens(mt);
-        }
-        if let Some(ref tc) = req.tool_choice
-            && let Some(choice) = normalize_tool_choice(tc)
-        {
-            tool_req = tool_req.with_tool_choice(choice);
-        }
-        LlmResult::WithTools(
-            llm.complete_with_tools(tool_req)
-                .await
-                .map_err(map_llm_error)?,
-        )
+    let mut chunks = llm
+        .complete_stream(stream_req)
+        .await
+        .map_err(map_llm_error)?;
+    let model_name = llm.effective_model_name(Some(requested_model.as_str()));
+    let streaming_mode = if llm.supports_streaming() {
+        "native"
     } else {
-        let mut comp_req = CompletionRequest::new(messages).with_model(req.model);
-        if let Some(t) = req.temperature {
-            comp_req = comp_req.with_temperature(t);
-        }
-        if let Some(mt) = req.max_tokens {
-            comp_req = comp_req.with_max_tokens(mt);
-        }
-        if let Some(ref stop_val) = req.stop {
-            comp_req.stop_sequences = parse_stop(stop_val);
-        }
-        LlmResult::Simple(llm.complete(comp_req).await.map_err(map_llm_error)?)
+        "simulated"
     };
-    let model_name = llm.effective_model_name(Some(requested_model.as_str()));
 
-    // LLM succeeded — emit the response as SSE chunks
     let (tx, rx) = tokio::sync::mpsc::channel::<Result<Event, std::convert::Infallible>>(64);
 
     tokio::spawn(async move {
-        // Send initial chunk with role
-        let role_chunk = OpenAiChatChunk {
+        let chunk = |delta: OpenAiDelta, finish_reason: Option<String>| OpenAiChatChunk {
             id: id.clone(),
             object: "chat.completion.chunk",
             created,
             model: model_name.clone(),
             choices: vec![OpenAiChunkChoice {
                 index: 0,
-                delta: OpenAiDelta {
-                    role: Some("assistant".to_string()),
-                    content: None,
-                    tool_calls: None,
-                },
-                finish_reason: None,
+                delta,
+                finish_reason,
             }],
+            usage: None,
+        };
+        let send = |payload: String| {
+            let tx = tx.clone();
+            async move { tx.send(Ok(Event::default().data(payload))).await.is_ok() }
         };
-        let data = serde_json::to_string(&role_chunk).unwrap_or_default();
-        let _ = tx.send(Ok(Event::default().data(data))).await;
-
-        match llm_result {
-            LlmResult::WithTools(resp) => {
-                // Stream content chunks
-                if let Some(ref content) = resp.content {
-                    stream_content_chunks(&tx, &id, created, &model_name, content).await;
-                }
 
-                // Stream tool calls
-                if !resp.tool_calls.is_empty() {
-                    let deltas: Vec<OpenAiToolCallDelta> = resp
-                        .tool_calls
-                        .iter()
-                        .enumerate()
-                        .map(|(i, tc)| OpenAiToolCallDelta {
-                            index: i as u32,
-                            id: Some(tc.id.clone()),
-                            call_type: Some("function".to_string()),
-                            function: Some(OpenAiToolCallFunctionDelta {
-                                name: Some(tc.name.clone()),
-                                arguments: Some(
-                                    serde_json::to_string(&tc.arguments).unwrap_or_default(),
-                                ),
-                            }),
-                        })
-                        .collect();
-
-                    let chunk = OpenAiChatChunk {
-                        id: id.clone(),
-                        object: "chat.completion.chunk",
-                        created,
-                        model: model_name.clone(),
-                        choices: vec![OpenAiChunkChoice {
-                            index: 0,
-                            delta: OpenAiDelta {
-                                role: None,
-                                content: None,
-                                tool_calls: Some(deltas),
-                            },
-                            finish_reason: None,
-                        }],
-                    };
-                    let data = serde_json::to_string(&chunk).unwrap_or_default();
-                    let _ = tx.send(Ok(Event::default().data(data))).await;
-                }
+        // Initial chunk carries the role.
+        let role_chunk = chunk(
+            OpenAiDelta {
+                role: Some("assistant".to_string()),
+                content: None,
+                tool_calls: None,
+            },
+            None,
+        );
+        if !send(serde_json::to_string(&role_chunk).unwrap_or_default()).await {
+            return;
+        }
 
-                // Final chunk with finish_reason
-                send_finish_chunk(&tx, &id, created, &model_name, resp.finish_reason).await;
-            }
-            LlmResult::Simple(resp) => {
-                stream_content_chunks(&tx, &id, created, &model_name, &resp.content).await;
-                send_finish_chunk(&tx, &id, created, &model_name, resp.finish_reason).await;
+        while let Some(item) = chunks.next().await {
+            let payload = match item {
+                Ok(StreamChunk::Done {
+                    input_tokens,
+                    output_tokens,
+                    finish_reason,
+                }) => {
+                    let finish = chunk(
+                        OpenAiDelta::default(),
+                        Some(finish_reason_str(finish_reason)),
+                    );
+                    if !send(serde_json::to_string(&finish).unwrap_or_default()).await {
+                        return;
+                    }
+                    if include_usage {
+                        let mut usage_chunk = chunk(OpenAiDelta::default(), None);
+                        usage_chunk.choices.clear();
+                        usage_chunk.usage = Some(OpenAiUsage {
+                            prompt_tokens: input_tokens,
+                            completion_tokens: output_tokens,
+                            total_tokens: input_tokens + output_tokens,
+                        });
+                        if !send(serde_json::to_string(&usage_chunk).unwrap_or_default()).await {
+                            return;
+                        }
+                    }
+                    continue;
+                }
+                Ok(other) => match stream_chunk_to_delta(other) {
+                    Some(delta) => serde_json::to_string(&chunk(delta, None)).unwrap_or_default(),
+                    None => continue,
+                },
+                Err(err) => {
+                    tracing::warn!(error = %err, "LLM stream failed mid-response");
+                    let (_, Json(body)) = map_llm_error(err);
+                    let _ = send(serde_json::to_string(&body).unwrap_or_default()).await;
+                    break;
+                }
+            };
+            if !send(payload).await {
+                return;
             }
         }
 
         // Send [DONE] sentinel
-        let _ = tx.send(Ok(Event::default().data("[DONE]"))).await;
+        let _ = send("[DONE]".to_string()).await;
     });
 
     let stream = tokio_stream::wrappers::ReceiverStream::new(rx);
     let sse = Sse::new(stream).keep_alive(KeepAlive::new().text(""));
     let mut response = sse.into_response();
-    response
-        .headers_mut()
-        .insert("x-clawyer-streaming", HeaderValue::from_static("simulated"));
+    response.headers_mut().insert(
+        "x-clawyer-streaming",
+        HeaderValue::from_static(streaming_mode),
+    );
     Ok(response)
 }
 
-/// Split content into word-boundary chunks and send as SSE events.
-async fn stream_content_chunks(
-    tx: &tokio::sync::mpsc::Sender<Result<Event, std::convert::Infallible>>,
-    id: &str,
-    created: u64,
-    model: &str,
-    content: &str,
-) {
-    // Split on word boundaries, grouping ~20 chars per chunk
-    let mut buf = String::new();
-    for word in content.split_inclusive(char::is_whitespace) {
-        buf.push_str(word);
-        if buf.len() >= 20 {
-            let chunk = OpenAiChatChunk {
-                id: id.to_string(),
-                object: "chat.completion.chunk",
-                created,
-                model: model.to_string(),
-                choices: vec![OpenAiChunkChoice {
-                    index: 0,
-                    delta: OpenAiDelta {
-                        role: None,
-                        content: Some(buf.clone()),
-                        tool_calls: None,
-                    },
-                    finish_reason: None,
-                }],
-            };
-            let data = serde_json::to_string(&chunk).unwrap_or_default();
-            if tx.send(Ok(Event::default().data(data))).await.is_err() {
-                return;
-            }
-            buf.clear();
+/// Map a text or tool-call chunk to its OpenAI delta. `Done` is handled by
+/// the caller since it carries the finish reason and usage.
+fn stream_chunk_to_delta(chunk: StreamChunk) -> Option<OpenAiDelta> {
+    match chunk {
+        StreamChunk::Text(text) if !text.is_empty() => Some(OpenAiDelta {
+            content: Some(text),
+            ..OpenAiDelta::default()
+        }),
+        StreamChunk::ToolCall {
+            index,
+            id,
+            name,
+            arguments,
+        } => {
+            let call_type = id.as_ref().map(|_| "function".to_string());
+            let function = (name.is_some() || arguments.is_some())
+                .then_some(OpenAiToolCallFunctionDelta { name, arguments });
+            Some(OpenAiDelta {
+                tool_calls: Some(vec![OpenAiToolCallDelta {
+                    index,
+                    id,
+                    call_type,
+                    function,
+                }]),
+                ..OpenAiDelta::default()
+            })
         }
+        StreamChunk::Text(_) | StreamChunk::Done { .. } => None,
     }
-    // Flush remaining
-    if !buf.is_empty() {
-        let chunk = OpenAiChatChunk {
-            id: id.to_string(),
-            object: "chat.completion.chunk",
-            created,
-            model: model.to_string(),
-            choices: vec![OpenAiChunkChoice {
-                index: 0,
-                delta: OpenAiDelta {
-                    role: None,
-                    content: Some(buf),
-                    tool_calls: None,
-                },
-                finish_reason: None,
-            }],
-        };
-        let data = serde_json::to_string(&chunk).unwrap_or_default();
-        let _ = tx.send(Ok(Event::default().data(data))).await;
-    }
-}
-
-async fn send_finish_chunk(
-    tx: &tokio::sync::mpsc::Sender<Result<Event, std::convert::Infallible>>,
-    id: &str,
-    created: u64,
-    model: &str,
-    reason: FinishReason,
-) {
-    let chunk = OpenAiChatChunk {
-        id: id.to_string(),
-        object: "chat.completion.chunk",
-        created,
-        model: model.to_string(),
-        choices: vec![OpenAiChunkChoice {
-            index: 0,
-            delta: OpenAiDelta {
-                role: None,
-                content: None,
-                tool_calls: None,
-            },
-            finish_reason: Some(finish_reason_str(reason)),
-        }],
-    };
-    let data = serde_json::to_string(&chunk).unwrap_or_default();
-    let _ = tx.send(Ok(Event::default().data(data))).await;
 }
 
 pub async fn models_handler(
@@ -1104,6 +1048,46 @@ mod tests {
         assert_eq!(parse_stop(&v), None);
     }
 
+    #[test]
+    fn test_stream_chunk_to_delta() {
+        let text = stream_chunk_to_delta(StreamChunk::Text("Hi".to_string())).unwrap();
+        assert_eq!(
+            serde_json::to_value(&text).unwrap(),
+            serde_json::json!({"content": "Hi"})
+        );
+
+        let first = stream_chunk_to_delta(StreamChunk::ToolCall {
+            index: 1,
+            id: Some("call_1".to_string()),
+            name: Some("search".to_string()),
+            arguments: None,
+        })
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(&first).unwrap(),
+            serde_json::json!({"tool_calls": [{
+                "index": 1,
+                "id": "call_1",
+                "type": "function",
+                "function": {"name": "search"}
+            }]})
+        );
+
+        let fragment = stream_chunk_to_delta(StreamChunk::ToolCall {
+            index: 1,
+            id: None,
+            name: None,
+            arguments: Some("{\"q\":".to_string()),
+        })
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(&fragment).unwrap(),
+            serde_json::json!({"tool_calls": [{"index": 1, "function": {"arguments": "{\"q\":"}}]})
+        );
+
+        assert!(stream_chunk_to_delta(StreamChunk::Text(String::new())).is_none());
+    }
+
     #[test]
     fn test_validate_model_name_rejects_leading_or_trailing_whitespace() {
         let err = validate_model_name(" gpt-4").unwrap_err();
@@ -0,0 +1,557 @@
+//! Per-user, per-route-group rate limiting for the web gateway.
+//!
+//! A single global window used to cover chat sends and the OpenAI-compatible
+//! endpoint, so a busy chat session throttled everything behind it. Requests
+//! are now counted in fixed windows keyed by the authenticated principal and
+//! the [`RouteGroup`] the route belongs to, so document uploads, chat, and
+//! the rest of the API each get their own budget per user.
+//!
+//! [`rate_limit_middleware`] runs inside the auth middleware and answers 429
+//! with a `Retry-After` header once a key's window is spent. Unauthenticated
+//! share-link downloads go through [`public_rate_limit_middleware`] instead,
+//! which keys on the client address so password guessing is throttled per
+//! caller.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderValue, Method, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::channels::web::auth::AuthPrincipal;
+
+/// Tracked keys above which expired windows are swept on the next request.
+const SWEEP_THRESHOLD: usize = 1024;
+
+/// Routes that share a budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteGroup {
+    /// Messages to the agent: `/api/chat/send` and `/v1/chat/completions`.
+    Chat,
+    /// Multipart uploads: `/api/memory/upload` and backup restores.
+    Uploads,
+    /// Everything else behind auth.
+    Api,
+    /// Public share-link downloads: `/api/share/{token}`, keyed by client
+    /// address.
+    Shares,
+}
+
+impl RouteGroup {
+    pub const ALL: [RouteGroup; 4] = [Self::Chat, Self::Uploads, Self::Api, Self::Shares];
+
+    /// Group a request by method and path.
+    pub fn classify(method: &Method, path: &str) -> Self {
+        if path.starts_with("/api/share/") {
+            return Self::Shares;
+        }
+        if method != Method::POST {
+            return Self::Api;
+        }
+        match path {
+            "/api/chat/send" | "/v1/chat/completions" => Self::Chat,
+            "/api/backups/restore" => Self::Uploads,
+            _ if path.ends_with("/upload") => Self::Uploads,
+            _ => Self::Api,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Chat => "chat",
+            Self::Uploads => "uploads",
+            Self::Api => "api",
+            Self::Shares => "shares",
+        }
+    }
+}
+
+/// Requests allowed per window. `max_requests == 0` disables the limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub max_requests: u64,
+    pub window: Duration,
+}
+
+impl RateLimit {
+    pub const fn per_minute(max_requests: u64) -> Self {
+        Self {
+            max_requests,
+            window: Duration::from_secs(60),
+        }
+    }
+
+    /// Parse `<requests>/<window_secs>` (e.g. `30/60`), or `off` / `0` to
+    /// disable the limit.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let raw = raw.trim();
+        if raw.eq_ignore_ascii_case("off") || raw == "0" {
+            return Ok(Self::per_minute(0));
+        }
+        let (requests, window) = raw
+            .split_once('/')
+            .ok_or_else(|| format!("expected <requests>/<window_secs>, got '{raw}'"))?;
+        let max_requests = requests
+            .trim()
+            .parse::<u64>()
+            .map_err(|e| format!("invalid request count '{requests}': {e}"))?;
+        let window_secs = window
+            .trim()
+            .parse::<u64>()
+            .map_err(|e| format!("invalid window '{window}': {e}"))?;
+        if window_secs == 0 {
+            return Err("window must be at least 1 second".to_string());
+        }
+        Ok(Self {
+            max_requests,
+            window: Duration::from_secs(window_secs),
+        })
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.max_requests == 0
+    }
+}
+
+/// Limits for each route group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    pub chat: RateLimit,
+    pub uploads: RateLimit,
+    pub api: RateLimit,
+    pub shares: RateLimit,
+}
+
+impl RateLimitConfig {
+    pub fn limit(&self, group: RouteGroup) -> RateLimit {
+        match group {
+            RouteGroup::Chat => self.chat,
+            RouteGroup::Uploads => self.uploads,
+            RouteGroup::Api => self.api,
+            RouteGroup::Shares => self.shares,
+        }
+    }
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            chat: RateLimit::per_minute(30),
+            uploads: RateLimit::per_minute(20),
+            api: RateLimit::per_minute(600),
+            shares: RateLimit::per_minute(30),
+        }
+    }
+}
+
+/// Point-in-time counters for one route group.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct RouteGroupStats {
+    pub group: RouteGroup,
+    pub max_requests: u64,
+    pub window_secs: u64,
+    pub allowed: u64,
+    pub limited: u64,
+}
+
+/// Point-in-time rate limiter metrics.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct RateLimitStats {
+    /// (principal, group) windows currently tracked.
+    pub tracked_keys: usize,
+    pub groups: Vec<RouteGroupStats>,
+}
+
+#[derive(Debug)]
+struct Window {
+    started: Instant,
+    count: u64,
+}
+
+#[derive(Debug, Default)]
+struct GroupCounters {
+    allowed: AtomicU64,
+    limited: AtomicU64,
+}
+
+/// Fixed-window limiter keyed by principal and route group.
+#[derive(Debug)]
+pub struct KeyedRateLimiter {
+    config: RateLimitConfig,
+    windows: Mutex<HashMap<(RouteGroup, String), Window>>,
+    counters: [GroupCounters; 4],
+}
+
+impl KeyedRateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            windows: Mutex::new(HashMap::new()),
+            counters: Default::default(),
+        }
+    }
+
+    pub fn config(&self) -> RateLimitConfig {
+        self.config
+    }
+
+    /// Count one request from `key` against `group`. On rejection returns how
+    /// long until the key's window resets.
+    pub fn check(&self, group: RouteGroup, key: &str) -> Result<(), Duration> {
+        self.check_at(group, key, Instant::now())
+    }
+
+    fn check_at(&self, group: RouteGroup, key: &str, now: Instant) -> Result<(), Duration> {
+        let limit = self.config.limit(group);
+        let counters = &self.counters[group as usize];
+        if limit.is_disabled() {
+            counters.allowed.fetch_add(1, Ordering::Relaxed);
+            return Ok(());
+        }
+
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        if windows.len() >= SWEEP_THRESHOLD {
+            let config = self.config;
+            windows.retain(|(group, _), w| {
+                now.duration_since(w.started) < config.limit(*group).window
+            });
+        }
+        let window = windows.entry((group, key.to_string())).or_insert(Window {
+            started: now,
+            count: 0,
+        });
+        let elapsed = now.duration_since(window.started);
+        if elapsed >= limit.window {
+            window.started = now;
+            window.count = 0;
+        }
+        if window.count >= limit.max_requests {
+            counters.limited.fetch_add(1, Ordering::Relaxed);
+            return Err(limit
+                .window
+                .saturating_sub(now.duration_since(window.started)));
+        }
+        window.count += 1;
+        counters.allowed.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Current metrics.
+    pub fn stats(&self) -> RateLimitStats {
+        let tracked_keys = self.windows.lock().unwrap_or_else(|e| e.into_inner()).len();
+        let groups = RouteGroup::ALL
+            .into_iter()
+            .map(|group| {
+                let limit = self.config.limit(group);
+                let counters = &self.counters[group as usize];
+                RouteGroupStats {
+                    group,
+                    max_requests: limit.max_requests,
+                    window_secs: limit.window.as_secs(),
+                    allowed: counters.allowed.load(Ordering::Relaxed),
+                    limited: counters.limited.load(Ordering::Relaxed),
+                }
+            })
+            .collect();
+        RateLimitStats {
+            tracked_keys,
+            groups,
+        }
+    }
+}
+
+impl Default for KeyedRateLimiter {
+    fn default() -> Self {
+        Self::new(RateLimitConfig::default())
+    }
+}
+
+/// Rate limit middleware. Must run after [`auth_middleware`] so requests are
+/// keyed by their principal; unauthenticated requests share one key.
+///
+/// [`auth_middleware`]: crate::channels::web::auth::auth_middleware
+pub async fn rate_limit_middleware(
+    State(limiter): State<Arc<KeyedRateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let group = RouteGroup::classify(request.method(), request.uri().path());
+    let key = request
+        .extensions()
+        .get::<AuthPrincipal>()
+        .map(|p| p.user_id.as_str())
+        .unwrap_or("anonymous");
+    let Err(retry_after) = limiter.check(group, key) else {
+        return next.run(request).await;
+    };
+
+    tracing::debug!(group = group.as_str(), key, "Gateway rate limit exceeded");
+    let retry_secs = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+    let mut response = if request.uri().path().starts_with("/v1/") {
+        crate::channels::web::openai_compat::openai_error(
+            StatusCode::TOO_MANY_REQUESTS,
+            format!("Rate limit exceeded. Retry in {retry_secs}s."),
+            "rate_limit_error",
+        )
+        .into_response()
+    } else {
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            format!("Rate limit exceeded. Retry in {retry_secs}s."),
+        )
+            .into_response()
+    };
+    if let Ok(value) = HeaderValue::from_str(&retry_secs.to_string()) {
+        response.headers_mut().insert(header::RETRY_AFTER, value);
+    }
+    response
+}
+
+/// Rate limit middleware for unauthenticated routes. Requests are keyed by
+/// the client address, so one caller guessing share-link passwords cannot
+/// spend another's budget.
+pub async fn public_rate_limit_middleware(
+    State(limiter): State<Arc<KeyedRateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let group = RouteGroup::classify(request.method(), request.uri().path());
+    let key = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| format!("ip:{}", addr.ip()))
+        .unwrap_or_else(|| "ip:unknown".to_string());
+    let Err(retry_after) = limiter.check(group, &key) else {
+        return next.run(request).await;
+    };
+
+    tracing::debug!(
+        group = group.as_str(),
+        key = key.as_str(),
+        "Public rate limit exceeded"
+    );
+    let retry_secs = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+    let mut response = (
+        StatusCode::TOO_MANY_REQUESTS,
+        format!("Rate limit exceeded. Retry in {retry_secs}s."),
+    )
+        .into_response();
+    if let Ok(value) = HeaderValue::from_str(&retry_secs.to_string()) {
+        response.headers_mut().insert(header::RETRY_AFTER, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::middleware;
+    use axum::routing::{get, post};
+    use tower::ServiceExt;
+
+    fn limiter(chat: u64, uploads: u64) -> KeyedRateLimiter {
+        KeyedRateLimiter::new(RateLimitConfig {
+            chat: RateLimit::per_minute(chat),
+            uploads: RateLimit::per_minute(uploads),
+            api: RateLimit::per_minute(0),
+            shares: RateLimit::per_minute(1),
+        })
+    }
+
+    #[test]
+    fn classify_routes_into_groups() {
+        assert_eq!(
+            RouteGroup::classify(&Method::POST, "/api/chat/send"),
+            RouteGroup::Chat
+        );
+        assert_eq!(
+            RouteGroup::classify(&Method::POST, "/v1/chat/completions"),
+            RouteGroup::Chat
+        );
+        assert_eq!(
+            RouteGroup::classify(&Method::POST, "/api/memory/upload"),
+            RouteGroup::Uploads
+        );
+        assert_eq!(
+            RouteGroup::classify(&Method::POST, "/api/backups/restore"),
+            RouteGroup::Uploads
+        );
+        assert_eq!(
+            RouteGroup::classify(&Method::GET, "/api/chat/send"),
+            RouteGroup::Api
+        );
+        assert_eq!(
+            RouteGroup::classify(&Method::POST, "/api/matters"),
+            RouteGroup::Api
+        );
+        assert_eq!(
+            RouteGroup::classify(&Method::GET, "/api/share/abc123"),
+            RouteGroup::Shares
+        );
+        assert_eq!(
+            RouteGroup::classify(&Method::POST, "/api/share/abc123"),
+            RouteGroup::Shares
+        );
+    }
+
+    #[test]
+    fn parse_limits() {
+        assert_eq!(
+            RateLimit::parse("30/60"),
+            Ok(RateLimit {
+                max_requests: 30,
+                window: Duration::from_secs(60),
+            })
+        );
+        assert!(RateLimit::parse("off").unwrap().is_disabled());
+        assert!(RateLimit::parse("0").unwrap().is_disabled());
+        assert!(RateLimit::parse("30").is_err());
+        assert!(RateLimit::parse("30/0").is_err());
+        assert!(RateLimit::parse("x/60").is_err());
+    }
+
+    #[test]
+    fn groups_and_users_have_separate_windows() {
+        let limiter = limiter(2, 1);
+        let now = Instant::now();
+        assert!(limiter.check_at(RouteGroup::Chat, "alice", now).is_ok());
+        assert!(limiter.check_at(RouteGroup::Chat, "alice", now).is_ok());
+        assert!(limiter.check_at(RouteGroup::Chat, "alice", now).is_err());
+
+        // A busy chat does not block uploads or other users.
+        assert!(limiter.check_at(RouteGroup::Uploads, "alice", now).is_ok());
+        assert!(limiter.check_at(RouteGroup::Chat, "bob", now).is_ok());
+        // Disabled groups never reject.
+        for _ in 0..100 {
+            assert!(limiter.check_at(RouteGroup::Api, "alice", now).is_ok());
+        }
+
+        let stats = limiter.stats();
+        let chat = &stats.groups[RouteGroup::Chat as usize];
+        assert_eq!((chat.allowed, chat.limited), (3, 1));
+        assert_eq!(stats.tracked_keys, 3);
+    }
+
+    #[test]
+    fn window_resets_and_reports_retry_after() {
+        let limiter = limiter(1, 1);
+        let start = Instant::now();
+        limiter.check_at(RouteGroup::Chat, "alice", start).unwrap();
+        let retry = limiter
+            .check_at(RouteGroup::Chat, "alice", start + Duration::from_secs(15))
+            .unwrap_err();
+        assert_eq!(retry, Duration::from_secs(45));
+        assert!(
+            limiter
+                .check_at(RouteGroup::Chat, "alice", start + Duration::from_secs(60))
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn middleware_returns_429_with_retry_after() {
+        async fn ok() -> StatusCode {
+            StatusCode::OK
+        }
+        let limiter = Arc::new(limiter(1, 1));
+        let app = Router::new()
+            .route("/api/chat/send", post(ok))
+            .route("/v1/chat/completions", post(ok))
+            .route("/api/jobs", get(ok))
+            .route_layer(middleware::from_fn_with_state(
+                Arc::clone(&limiter),
+                rate_limit_middleware,
+            ));
+        let send = |method: &str, uri: &str| {
+            axum::http::Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(axum::body::Body::empty())
+                .expect("valid request")
+        };
+
+        let response = app
+            .clone()
+            .oneshot(send("POST", "/api/chat/send"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .clone()
+            .oneshot(send("POST", "/api/chat/send"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = response.headers()[header::RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((1..=60).contains(&retry_after));
+
+        // The OpenAI-compatible route shares the chat budget and answers in
+        // its own error format.
+        let response = app
+            .clone()
+            .oneshot(send("POST", "/v1/chat/completions"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["type"], "rate_limit_error");
+
+        let response = app.oneshot(send("GET", "/api/jobs")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn public_middleware_keys_on_client_address() {
+        async fn ok() -> StatusCode {
+            StatusCode::OK
+        }
+        let limiter = Arc::new(limiter(1, 1));
+        let app = Router::new()
+            .route("/api/share/{token}", post(ok))
+            .route_layer(middleware::from_fn_with_state(
+                Arc::clone(&limiter),
+                public_rate_limit_middleware,
+            ));
+        let from = |ip: [u8; 4]| {
+            let mut request = axum::http::Request::builder()
+                .method("POST")
+                .uri("/api/share/abc123")
+                .body(axum::body::Body::empty())
+                .expect("valid request");
+            request
+                .extensions_mut()
+                .insert(ConnectInfo(SocketAddr::from((ip, 40_000))));
+            request
+        };
+
+        let response = app.clone().oneshot(from([203, 0, 113, 7])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = app.clone().oneshot(from([203, 0, 113, 7])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(response.headers().contains_key(header::RETRY_AFTER));
+
+        // Another caller still has its own budget.
+        let response = app.oneshot(from([198, 51, 100, 2])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let shares = &limiter.stats().groups[RouteGroup::Shares as usize];
+        assert_eq!((shares.allowed, shares.limited), (2, 1));
+    }
+}
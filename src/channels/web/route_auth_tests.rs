@@ -0,0 +1,731 @@
+//! Per-route authorization tests driven through the full gateway router.
+//!
+//! Each case is sent over [`RouteHarness`], so the auth middleware, route
+//! matching, extractors, and handler RBAC checks are all exercised together.
+
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+use crate::channels::web::test_support::*;
+
+/// Minimum matter role a route requires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MinRole {
+    Viewer,
+    Collaborator,
+    Owner,
+}
+
+struct MatterRouteCase {
+    method: Method,
+    path: &'static str,
+    body: Option<serde_json::Value>,
+    min_role: MinRole,
+    /// Status returned when access is granted.
+    ok: StatusCode,
+}
+
+fn matter_route_cases() -> Vec<MatterRouteCase> {
+    let case = |method: Method, path, body, min_role, ok| MatterRouteCase {
+        method,
+        path,
+        body,
+        min_role,
+        ok,
+    };
+    vec![
+        case(
+            Method::GET,
+            "/api/matters/demo/tasks",
+            None,
+            MinRole::Viewer,
+            StatusCode::OK,
+        ),
+        case(
+            Method::POST,
+            "/api/matters/demo/tasks",
+            Some(json!({ "title": "Draft answer" })),
+            MinRole::Collaborator,
+            StatusCode::CREATED,
+        ),
+        case(
+            Method::GET,
+            "/api/matters/demo/notes",
+            None,
+            MinRole::Viewer,
+            StatusCode::OK,
+        ),
+        case(
+            Method::POST,
+            "/api/matters/demo/notes",
+            Some(json!({ "author": "Lead", "body": "Call client" })),
+            MinRole::Collaborator,
+            StatusCode::CREATED,
+        ),
+        case(
+            Method::GET,
+            "/api/matters/demo/deadlines",
+            None,
+            MinRole::Viewer,
+            StatusCode::OK,
+        ),
+        case(
+            Method::POST,
+            "/api/matters/demo/deadlines",
+            Some(json!({
+                "title": "Answer due",
+                "deadline_type": "response_due",
+                "due_at": "2030-01-15T17:00:00Z",
+            })),
+            MinRole::Collaborator,
+            StatusCode::CREATED,
+        ),
+        case(
+            Method::GET,
+            "/api/matters/demo/time",
+            None,
+            MinRole::Viewer,
+            StatusCode::OK,
+        ),
+        case(
+            Method::POST,
+            "/api/matters/demo/time",
+            Some(json!({
+                "timekeeper": "Lead",
+                "description": "Review pleadings",
+                "hours": "1.5",
+                "entry_date": "2030-01-10",
+            })),
+            MinRole::Collaborator,
+            StatusCode::CREATED,
+        ),
+        case(
+            Method::GET,
+            "/api/matters/demo/expenses",
+            None,
+            MinRole::Viewer,
+            StatusCode::OK,
+        ),
+        case(
+            Method::GET,
+            "/api/matters/demo/members",
+            None,
+            MinRole::Owner,
+            StatusCode::OK,
+        ),
+    ]
+}
+
+fn granted_role(user: HarnessUser) -> Option<MinRole> {
+    match user.user_id {
+        id if id == HARNESS_OWNER.user_id => Some(MinRole::Owner),
+        id if id == HARNESS_COLLABORATOR.user_id => Some(MinRole::Collaborator),
+        id if id == HARNESS_VIEWER.user_id => Some(MinRole::Viewer),
+        _ => None,
+    }
+}
+
+#[tokio::test]
+async fn protected_routes_reject_missing_or_unknown_tokens() {
+    let harness = RouteHarness::new().await;
+    let (status, _) = harness
+        .request(Method::GET, "/api/health", None, None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    for path in [
+        "/api/matters",
+        "/api/matters/demo/tasks",
+        "/api/billing/rates",
+        "/api/legal/audit",
+        "/metrics",
+    ] {
+        let (status, _) = harness.request(Method::GET, path, None, None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED, "GET {path} without token");
+
+        let forged = HarnessUser {
+            user_id: "nobody",
+            token: "not-a-real-token",
+        };
+        let status = harness.status(Method::GET, path, forged, None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED, "GET {path} forged token");
+    }
+}
+
+#[tokio::test]
+async fn metrics_endpoint_serves_prometheus_text() {
+    crate::observability::metrics::install_prometheus_recorder();
+    let harness = RouteHarness::new().await;
+    harness
+        .request(Method::GET, "/api/health", None, None)
+        .await;
+
+    let (status, body) = harness
+        .request(Method::GET, "/metrics", Some(HARNESS_OWNER), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let text = body.as_str().expect("plain-text exposition");
+    assert!(
+        text.contains(
+            r#"clawyer_http_requests_total{method="GET",route="/api/health",status="200"}"#
+        ),
+        "{text}"
+    );
+    // Refreshed by the handler on every scrape.
+    assert!(text.contains(r#"clawyer_intake_queue_depth{channel="gateway"} 0"#));
+}
+
+#[tokio::test]
+async fn matter_routes_enforce_minimum_member_role() {
+    let harness = RouteHarness::new().await;
+    for user in [
+        HARNESS_OWNER,
+        HARNESS_COLLABORATOR,
+        HARNESS_VIEWER,
+        HARNESS_OUTSIDER,
+    ] {
+        for case in matter_route_cases() {
+            let allowed = granted_role(user).is_some_and(|role| role >= case.min_role);
+            let expected = if allowed {
+                case.ok
+            } else {
+                StatusCode::FORBIDDEN
+            };
+            let status = harness
+                .status(case.method.clone(), case.path, user, case.body.clone())
+                .await;
+            assert_eq!(
+                status, expected,
+                "{} {} as {}",
+                case.method, case.path, user.user_id
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn matter_listing_is_restricted_to_the_gateway_owner() {
+    let harness = RouteHarness::new().await;
+    let (status, body) = harness
+        .request(Method::GET, "/api/matters", Some(HARNESS_OWNER), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["matters"][0]["id"], HARNESS_MATTER_ID);
+
+    for user in [HARNESS_COLLABORATOR, HARNESS_VIEWER, HARNESS_OUTSIDER] {
+        let status = harness
+            .status(Method::GET, "/api/matters", user, None)
+            .await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "list as {}", user.user_id);
+    }
+}
+
+#[tokio::test]
+async fn admin_only_routes_reject_non_admin_principals() {
+    let harness = RouteHarness::new().await;
+    let role_body = Some(json!({ "role": "viewer" }));
+    for user in [HARNESS_COLLABORATOR, HARNESS_VIEWER, HARNESS_OUTSIDER] {
+        let status = harness
+            .status(
+                Method::POST,
+                "/api/billing/rates",
+                user,
+                Some(json!({
+                    "timekeeper": "Lead",
+                    "rate": "300.00",
+                    "effective_start": "2030-01-01",
+                })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "rates as {}", user.user_id);
+
+        let status = harness
+            .status(
+                Method::PUT,
+                "/api/users/mallory/role",
+                user,
+                role_body.clone(),
+            )
+            .await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "role as {}", user.user_id);
+
+        let status = harness
+            .status(Method::GET, "/api/system/backups", user, None)
+            .await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "backups as {}", user.user_id);
+    }
+
+    let status = harness
+        .status(
+            Method::PUT,
+            "/api/users/mallory/role",
+            HARNESS_OWNER,
+            role_body,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let demoted = harness
+        .db
+        .get_user_account("mallory")
+        .await
+        .expect("load user")
+        .expect("user exists");
+    assert_eq!(demoted.role, crate::db::UserRole::Viewer);
+}
+
+#[tokio::test]
+async fn membership_changes_take_effect_on_the_next_request() {
+    let harness = RouteHarness::new().await;
+    let path = "/api/matters/demo/tasks";
+    assert_eq!(
+        harness
+            .status(Method::GET, path, HARNESS_OUTSIDER, None)
+            .await,
+        StatusCode::FORBIDDEN
+    );
+
+    let status = harness
+        .status(
+            Method::PUT,
+            "/api/matters/demo/members/mallory",
+            HARNESS_OWNER,
+            Some(json!({ "role": "viewer" })),
+        )
+        .await;
+    assert!(status.is_success(), "add member: {status}");
+    assert_eq!(
+        harness
+            .status(Method::GET, path, HARNESS_OUTSIDER, None)
+            .await,
+        StatusCode::OK
+    );
+
+    let status = harness
+        .status(
+            Method::DELETE,
+            "/api/matters/demo/members/mallory",
+            HARNESS_OWNER,
+            None,
+        )
+        .await;
+    assert!(status.is_success(), "remove member: {status}");
+    assert_eq!(
+        harness
+            .status(Method::GET, path, HARNESS_OUTSIDER, None)
+            .await,
+        StatusCode::FORBIDDEN
+    );
+}
+
+#[tokio::test]
+async fn organization_members_reach_shared_matters() {
+    let harness = RouteHarness::new().await;
+    let path = "/api/matters/demo/tasks";
+    let (status, org) = harness
+        .request(
+            Method::POST,
+            "/api/orgs",
+            Some(HARNESS_OWNER),
+            Some(json!({ "name": "Demo Firm" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let org_id = org["id"].as_str().expect("org id").to_string();
+
+    let status = harness
+        .status(
+            Method::PUT,
+            &format!("/api/orgs/{org_id}/members/mallory"),
+            HARNESS_OUTSIDER,
+            Some(json!({ "role": "member" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "non-member cannot self-join");
+    let status = harness
+        .status(
+            Method::PUT,
+            &format!("/api/orgs/{org_id}/members/mallory"),
+            HARNESS_OWNER,
+            Some(json!({ "role": "member" })),
+        )
+        .await;
+    assert!(status.is_success(), "add org member: {status}");
+    assert_eq!(
+        harness
+            .status(Method::GET, path, HARNESS_OUTSIDER, None)
+            .await,
+        StatusCode::FORBIDDEN,
+        "org membership alone does not expose private matters"
+    );
+
+    let share = |org_id: serde_json::Value| {
+        harness.status(
+            Method::PUT,
+            "/api/matters/demo/organization",
+            HARNESS_OWNER,
+            Some(json!({ "org_id": org_id })),
+        )
+    };
+    assert_eq!(share(json!(org_id)).await, StatusCode::OK);
+    assert_eq!(
+        harness
+            .status(Method::GET, path, HARNESS_OUTSIDER, None)
+            .await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        harness
+            .status(
+                Method::GET,
+                "/api/matters/demo/members",
+                HARNESS_OUTSIDER,
+                None
+            )
+            .await,
+        StatusCode::FORBIDDEN,
+        "org members collaborate but do not own the matter"
+    );
+
+    assert_eq!(share(serde_json::Value::Null).await, StatusCode::OK);
+    assert_eq!(
+        harness
+            .status(Method::GET, path, HARNESS_OUTSIDER, None)
+            .await,
+        StatusCode::FORBIDDEN
+    );
+}
+
+#[tokio::test]
+async fn organization_members_list_shared_clients() {
+    let harness = RouteHarness::new().await;
+    let (status, org) = harness
+        .request(
+            Method::POST,
+            "/api/orgs",
+            Some(HARNESS_OWNER),
+            Some(json!({ "name": "Demo Firm" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let org_id = org["id"].as_str().expect("org id").to_string();
+    let status = harness
+        .status(
+            Method::PUT,
+            &format!("/api/orgs/{org_id}/members/mallory"),
+            HARNESS_OWNER,
+            Some(json!({ "role": "member" })),
+        )
+        .await;
+    assert!(status.is_success(), "add org member: {status}");
+    let (status, client) = harness
+        .request(
+            Method::POST,
+            "/api/clients",
+            Some(HARNESS_OWNER),
+            Some(json!({ "name": "Acme", "client_type": "entity" })),
+        )
+        .await;
+    assert!(status.is_success(), "create client: {status}");
+    let client_path = format!(
+        "/api/clients/{}/organization",
+        client["id"].as_str().expect("client id")
+    );
+    let clients_path = format!("/api/orgs/{org_id}/clients");
+
+    assert_eq!(
+        harness
+            .status(
+                Method::PUT,
+                &client_path,
+                HARNESS_OUTSIDER,
+                Some(json!({ "org_id": org_id }))
+            )
+            .await,
+        StatusCode::FORBIDDEN,
+        "only the owning user shares clients"
+    );
+    let (status, body) = harness
+        .request(
+            Method::PUT,
+            &client_path,
+            Some(HARNESS_OWNER),
+            Some(json!({ "org_id": org_id })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.get("documents_updated").is_none());
+
+    let (status, listed) = harness
+        .request(Method::GET, &clients_path, Some(HARNESS_OUTSIDER), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(listed["clients"][0]["name"], "Acme");
+    assert_eq!(
+        harness
+            .status(Method::GET, &clients_path, HARNESS_VIEWER, None)
+            .await,
+        StatusCode::FORBIDDEN,
+        "non-members cannot list the org's clients"
+    );
+}
+
+#[tokio::test]
+async fn organizations_keep_at_least_one_owner() {
+    let harness = RouteHarness::new().await;
+    let (status, org) = harness
+        .request(
+            Method::POST,
+            "/api/orgs",
+            Some(HARNESS_OWNER),
+            Some(json!({ "name": "Demo Firm" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let org_id = org["id"].as_str().expect("org id").to_string();
+    let owner_path = format!("/api/orgs/{org_id}/members/test-user");
+    let demote = json!({ "role": "admin" });
+
+    assert_eq!(
+        harness
+            .status(
+                Method::PUT,
+                &owner_path,
+                HARNESS_OWNER,
+                Some(demote.clone())
+            )
+            .await,
+        StatusCode::CONFLICT,
+        "last owner cannot demote themselves"
+    );
+    assert_eq!(
+        harness
+            .status(Method::DELETE, &owner_path, HARNESS_OWNER, None)
+            .await,
+        StatusCode::CONFLICT,
+        "last owner cannot leave"
+    );
+
+    let status = harness
+        .status(
+            Method::PUT,
+            &format!("/api/orgs/{org_id}/members/alice"),
+            HARNESS_OWNER,
+            Some(json!({ "role": "owner" })),
+        )
+        .await;
+    assert!(status.is_success(), "add second owner: {status}");
+    assert_eq!(
+        harness
+            .status(Method::PUT, &owner_path, HARNESS_OWNER, Some(demote))
+            .await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        harness
+            .status(
+                Method::DELETE,
+                &format!("/api/orgs/{org_id}/members/alice"),
+                HARNESS_COLLABORATOR,
+                None
+            )
+            .await,
+        StatusCode::CONFLICT,
+        "the remaining owner cannot leave either"
+    );
+}
+
+#[tokio::test]
+async fn routine_webhooks_authenticate_with_the_url_token() {
+    let harness = RouteHarness::new().await;
+    let (status, created) = harness
+        .request(
+            Method::POST,
+            "/api/routines",
+            Some(HARNESS_OWNER),
+            Some(json!({
+                "name": "crm-intake",
+                "trigger_type": "webhook",
+                "prompt": "Run a conflict pre-check for the new lead",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let id = created["id"].as_str().expect("routine id");
+
+    let (status, detail) = harness
+        .request(
+            Method::GET,
+            &format!("/api/routines/{id}"),
+            Some(HARNESS_OWNER),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let path = detail["webhook_path"].as_str().expect("webhook path");
+    assert!(path.starts_with(&format!("/api/routines/{id}/webhook/")));
+
+    let lead = json!({ "lead": "Acme Corp" });
+    let (status, _) = harness
+        .request(
+            Method::POST,
+            &format!("/api/routines/{id}/webhook/wrong-token"),
+            None,
+            Some(lead.clone()),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // The right token is accepted without a bearer token; the harness has no
+    // routine engine running, so the fire itself is unavailable.
+    let (status, _) = harness.request(Method::POST, path, None, Some(lead)).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn share_links_serve_documents_without_a_bearer_token() {
+    let harness = RouteHarness::new().await;
+    let document = "matters/demo/templates/research_memo.md";
+    let share =
+        |user, body| harness.request(Method::POST, "/api/memory/share", Some(user), Some(body));
+
+    let (status, _) = share(HARNESS_VIEWER, json!({ "path": document })).await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "viewers cannot share");
+    let (status, open) = share(
+        HARNESS_COLLABORATOR,
+        json!({ "path": document, "recipient": "Co-Counsel LLP" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let url = open["url"].as_str().expect("share url").to_string();
+
+    let (status, body) = harness.request(Method::GET, &url, None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let text = body.as_str().expect("document body");
+    assert!(text.contains("# Research Memo Template"));
+    assert!(text.contains("CONFIDENTIAL - shared for Co-Counsel LLP"));
+    let (status, _) = harness
+        .request(Method::GET, "/api/share/not-a-token", None, None)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, locked) = share(
+        HARNESS_OWNER,
+        json!({ "path": document, "password": "s3cret-pass", "watermark": false }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let locked_url = locked["url"].as_str().expect("share url").to_string();
+    let (status, _) = harness.request(Method::GET, &locked_url, None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = harness
+        .request(
+            Method::POST,
+            &locked_url,
+            None,
+            Some(json!({ "password": "wrong-pass" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, body) = harness
+        .request(
+            Method::POST,
+            &locked_url,
+            None,
+            Some(json!({ "password": "s3cret-pass" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_str(), Some("# Research Memo Template\n"));
+
+    let (status, listed) = harness
+        .request(
+            Method::GET,
+            &format!("/api/memory/share?path={document}"),
+            Some(HARNESS_VIEWER),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let links = listed["links"].as_array().expect("links");
+    assert_eq!(links.len(), 2);
+    assert!(links.iter().all(|link| link["download_count"] == 1));
+    let (status, listed) = harness
+        .request(
+            Method::GET,
+            "/api/memory/share",
+            Some(HARNESS_OUTSIDER),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(listed["links"], json!([]), "non-members see no links");
+
+    let id = open["id"].as_str().expect("share id");
+    let status = harness
+        .status(
+            Method::DELETE,
+            &format!("/api/memory/share/{id}"),
+            HARNESS_COLLABORATOR,
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = harness.request(Method::GET, &url, None, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "revoked links stop working");
+}
+
+#[tokio::test]
+async fn share_links_back_off_after_repeated_wrong_passwords() {
+    let harness = RouteHarness::new().await;
+    let document = "matters/demo/templates/research_memo.md";
+    let (status, created) = harness
+        .request(
+            Method::POST,
+            "/api/memory/share",
+            Some(HARNESS_OWNER),
+            Some(json!({ "path": document, "password": "s3cret-pass" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let url = created["url"].as_str().expect("share url").to_string();
+
+    for _ in 0..crate::channels::web::handlers::shares::FREE_PASSWORD_ATTEMPTS {
+        let (status, _) = harness
+            .request(
+                Method::POST,
+                &url,
+                None,
+                Some(json!({ "password": "wrong-pass" })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+    let (status, _) = harness
+        .request(
+            Method::POST,
+            &url,
+            None,
+            Some(json!({ "password": "s3cret-pass" })),
+        )
+        .await;
+    assert_eq!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS,
+        "guesses wait out the backoff, even the right one"
+    );
+
+    let (status, listed) = harness
+        .request(
+            Method::GET,
+            &format!("/api/memory/share?path={document}"),
+            Some(HARNESS_OWNER),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        listed["links"][0]["status"], "active",
+        "wrong passwords never revoke the link"
+    );
+    assert_eq!(listed["links"][0]["failed_password_attempts"], 3);
+}
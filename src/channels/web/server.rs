@@ -7,9 +7,10 @@ use std::sync::Arc;
 
 use axum::{
     Router,
-    extract::DefaultBodyLimit,
+    extract::{DefaultBodyLimit, MatchedPath, Request},
     http::{HeaderValue, header},
-    middleware,
+    middleware::{self, Next},
+    response::Response,
     routing::{get, post},
 };
 use serde::Deserialize;
@@ -20,7 +21,9 @@ use tower_http::set_header::SetResponseHeaderLayer;
 use crate::channels::web::auth::{
     AuthPrincipal, AuthState, auth_middleware, compute_token_hash, derive_token_hmac_key,
 };
-pub use crate::channels::web::state::{GatewayState, PromptQueue, RateLimiter};
+pub use crate::channels::web::rate_limit::{KeyedRateLimiter, RateLimitConfig};
+use crate::channels::web::rate_limit::{public_rate_limit_middleware, rate_limit_middleware};
+pub use crate::channels::web::state::{GatewayState, PromptQueue};
 use crate::channels::web::types::*;
 use crate::db::UserRole;
 
@@ -48,10 +51,6 @@ pub async fn start_server(
                 reason: format!("Failed to get local addr: {}", e),
             })?;
 
-    // Public routes (no auth)
-    let public = crate::channels::web::handlers::routes::public_routes();
-
-    // Protected routes (require auth)
     let principal = resolve_gateway_principal(state.as_ref()).await?;
     // Derive a stable HMAC-SHA256 key from the gateway auth token. The key is
     // deterministic (same token → same key) so no extra configuration is needed.
@@ -77,6 +76,50 @@ pub async fn start_server(
         store: state.store.clone(),
         hmac_key: Some(hmac_key),
     };
+    let app = gateway_router(state.clone(), auth_state, addr)?;
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    *state.shutdown_tx.write().await = Some(shutdown_tx);
+
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(async {
+            let _ = shutdown_rx.await;
+            tracing::info!("Web gateway shutting down");
+        })
+        .await
+        {
+            tracing::error!("Web gateway server error: {}", e);
+        }
+    });
+
+    Ok(bound_addr)
+}
+
+/// Assemble the full gateway router (public, static, project, and protected
+/// routes plus CORS and security headers) without binding a listener.
+///
+/// `addr` is only used to derive the allowed CORS origins.
+pub(crate) fn gateway_router(
+    state: Arc<GatewayState>,
+    auth_state: AuthState,
+    addr: SocketAddr,
+) -> Result<Router, crate::error::ChannelError> {
+    // Public routes (no auth). Share-link downloads are throttled per client
+    // address so link passwords cannot be guessed at full speed.
+    let public = crate::channels::web::handlers::routes::public_routes().merge(
+        crate::channels::web::handlers::shares::public_routes().route_layer(
+            middleware::from_fn_with_state(
+                Arc::clone(&state.rate_limiter),
+                public_rate_limit_middleware,
+            ),
+        ),
+    );
+
+    // Protected routes (require auth)
     let protected = Router::new()
         .merge(crate::channels::web::handlers::routes::protected_feature_routes())
         // OpenAI-compatible API
@@ -88,6 +131,12 @@ pub async fn start_server(
             "/v1/models",
             get(crate::channels::web::openai_compat::models_handler),
         )
+        // Layers run bottom-up: auth first, so the limiter can key on the
+        // authenticated principal.
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&state.rate_limiter),
+            rate_limit_middleware,
+        ))
         .route_layer(middleware::from_fn_with_state(
             auth_state.clone(),
             auth_middleware,
@@ -141,6 +190,7 @@ pub async fn start_server(
         .merge(statics)
         .merge(projects)
         .merge(protected)
+        .layer(middleware::from_fn(track_http_metrics))
         .layer(DefaultBodyLimit::max(1024 * 1024)) // 1 MB max request body
         .layer(cors)
         .layer(SetResponseHeaderLayer::if_not_present(
@@ -155,24 +205,28 @@ pub async fn start_server(
             header::X_FRAME_OPTIONS,
             header::HeaderValue::from_static("DENY"),
         ))
-        .with_state(state.clone());
-
-    let (shutdown_tx, shutdown_rx) = oneshot::channel();
-    *state.shutdown_tx.write().await = Some(shutdown_tx);
+        .with_state(state);
 
-    tokio::spawn(async move {
-        if let Err(e) = axum::serve(listener, app)
-            .with_graceful_shutdown(async {
-                let _ = shutdown_rx.await;
-                tracing::info!("Web gateway shutting down");
-            })
-            .await
-        {
-            tracing::error!("Web gateway server error: {}", e);
-        }
-    });
+    Ok(app)
+}
 
-    Ok(bound_addr)
+/// Record request count and latency per matched route template.
+async fn track_http_metrics(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let started = std::time::Instant::now();
+    let response = next.run(request).await;
+    crate::observability::metrics::record_http_request(
+        method.as_str(),
+        &route,
+        response.status().as_u16(),
+        started.elapsed(),
+    );
+    response
 }
 
 async fn resolve_gateway_principal(
@@ -351,6 +405,8 @@ pub(crate) struct TreeQuery {
 #[derive(Deserialize)]
 pub(crate) struct ListQuery {
     pub(crate) path: Option<String>,
+    /// Comma-separated `key[=value]` tag filter.
+    pub(crate) tag: Option<String>,
 }
 
 #[derive(Deserialize)]
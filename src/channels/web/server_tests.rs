@@ -4,29 +4,61 @@ use std::sync::Arc;
 use crate::channels::web::auth::hash_auth_token;
 use crate::channels::web::handlers::{
     chat::{
-        chat_approval_handler, chat_history_handler, chat_new_thread_handler, chat_send_handler,
-        chat_threads_handler,
+        chat_approval_handler, chat_branch_thread_handler, chat_file_to_matter_handler,
+        chat_history_handler, chat_new_thread_handler, chat_send_handler,
+        chat_thread_config_handler, chat_thread_config_patch_handler, chat_threads_handler,
+    },
+    dashboard::dashboard_handler,
+    email::email_inbound_handler,
+    integrations::{
+        webhooks_create_handler, webhooks_deliveries_handler, webhooks_list_handler,
+        webhooks_test_handler, webhooks_update_handler,
+    },
+    jobs::{
+        EstimationAccuracyQuery, JobLogStreamQuery, jobs_artifact_download_handler,
+        jobs_artifact_promote_handler, jobs_artifacts_list_handler, jobs_dead_letter_handler,
+        jobs_estimation_accuracy_handler, jobs_logs_stream_handler,
     },
     legal::{
         compliance_letter_handler, compliance_status_handler, legal_audit_list_handler,
-        legal_court_rules_handler,
+        legal_cost_cap_put_handler, legal_costs_handler, legal_court_rules_handler,
+        legal_immigration_forms_handler, legal_ip_rules_handler,
     },
     matters::{
+        client_comms::{
+            client_communications_get_handler, client_communications_put_handler,
+            client_consent_record_handler,
+        },
+        closeout::{matter_close_handler, matter_closeout_status_handler},
+        compare::matter_documents_compare_handler,
         conflicts::{
-            matter_conflicts_clearance_handler, matter_conflicts_report_handler,
-            matter_parties_upsert_handler, matters_conflict_check_handler,
-            matters_conflicts_check_handler, matters_conflicts_reindex_handler,
+            legal_conflicts_bulk_check_handler, matter_conflicts_clearance_handler,
+            matter_conflicts_report_handler, matter_entities_handler,
+            matter_parties_upsert_handler, matter_party_candidate_accept_handler,
+            matter_party_candidate_reject_handler, matter_party_candidates_list_handler,
+            matters_conflict_check_handler, matters_conflicts_check_handler,
+            matters_conflicts_reindex_handler,
         },
         core::{
-            matter_deadline_override_handler, matter_deadlines_compute_handler,
-            matter_deadlines_create_handler, matter_deadlines_delete_handler,
-            matter_deadlines_handler, matter_deadlines_patch_handler, matters_active_get_handler,
+            clients_create_handler, matter_deadline_override_handler,
+            matter_deadlines_compute_handler, matter_deadlines_create_handler,
+            matter_deadlines_delete_handler, matter_deadlines_handler,
+            matter_deadlines_patch_handler, matter_patch_handler, matter_stage_get_handler,
+            matter_stage_transition_handler, matters_active_get_handler,
             matters_active_set_handler, matters_create_handler, matters_list_handler,
         },
         documents::{
-            document_citations_handler, document_ready_handler, documents_generate_handler,
-            matter_citations_verify_handler, matter_dashboard_handler, matter_documents_handler,
-            matter_filing_package_handler, matter_template_apply_handler, matter_templates_handler,
+            document_caption_handler, document_citations_handler, document_ready_handler,
+            documents_generate_handler, matter_citations_verify_handler, matter_dashboard_handler,
+            matter_documents_handler, matter_filing_package_handler,
+            matter_filing_validation_handler, matter_storage_handler,
+            matter_template_apply_handler, matter_templates_handler,
+            matter_translation_certify_handler, matter_translation_detail_handler,
+            matter_translation_request_certification_handler, matter_translations_handler,
+            template_variables_handler, template_variables_update_handler,
+        },
+        exhibits::{
+            matter_exhibit_mark_handler, matter_exhibit_withdraw_handler, matter_exhibits_handler,
         },
         finance::{
             billing_rates_create_handler, billing_rates_list_handler, billing_rates_patch_handler,
@@ -39,12 +71,57 @@ use crate::channels::web::handlers::{
             trust_reconciliations_compute_handler, trust_reconciliations_signoff_handler,
             trust_statements_import_handler,
         },
+        immigration::{matter_immigration_form_export_handler, matter_immigration_form_handler},
+        ip_docket::matter_ip_docket_handler,
+        medical::{medical_chronology_export_handler, medical_chronology_handler},
+        precedents::{
+            matter_precedent_anonymize_handler, matter_precedent_promote_handler,
+            matter_precedent_suggest_handler, precedents_list_handler,
+        },
+        settlement::{
+            matter_settlement_handler, settlement_authority_check_handler,
+            settlement_authority_create_handler, settlement_history_export_handler,
+            settlement_proposal_create_handler, settlement_proposal_status_handler,
+        },
+        status_reports::{
+            matter_status_report_approve_handler, matter_status_report_detail_handler,
+            matter_status_report_reject_handler, matter_status_reports_handler,
+        },
+        timeline::{matter_timeline_export_handler, matter_timeline_handler},
         work::{
-            matter_notes_create_handler, matter_notes_list_handler, matter_tasks_create_handler,
-            matter_tasks_list_handler,
+            matter_chronology_handler, matter_notes_create_handler, matter_notes_delete_handler,
+            matter_notes_list_handler, matter_notes_patch_handler, matter_tasks_board_handler,
+            matter_tasks_board_move_handler, matter_tasks_board_reorder_handler,
+            matter_tasks_create_handler, matter_tasks_delete_handler, matter_tasks_list_handler,
+            matter_tasks_patch_handler,
         },
     },
-    memory::memory_write_handler,
+    memory::{
+        memory_chunk_handler, memory_delete_handler, memory_delete_tag_handler,
+        memory_embedding_status_handler, memory_git_import_handler, memory_git_status_handler,
+        memory_git_sync_handler, memory_history_handler, memory_history_restore_handler,
+        memory_list_handler, memory_read_handler, memory_search_handler, memory_set_tags_handler,
+        memory_tag_facets_handler, memory_tags_handler, memory_trash_list_handler,
+        memory_trash_purge_handler, memory_trash_restore_handler, memory_upload_handler,
+        memory_write_handler, resolve_chunk_citations,
+    },
+    notifications::{
+        notification_ack_handler, notification_delivery_set_handler, notification_read_handler,
+        notification_snooze_handler, notifications_list_handler,
+    },
+    review::{
+        review_draft_approve_handler, review_draft_edit_handler, review_draft_reject_handler,
+        review_drafts_list_handler,
+    },
+    search::global_search_handler,
+    settings::{custom_field_schemas_get_handler, custom_field_schemas_put_handler},
+    templates::{
+        shared_template_delete_handler, shared_template_restore_handler,
+        shared_template_update_handler, shared_template_versions_handler,
+        shared_templates_create_handler, shared_templates_handler, template_analytics_handler,
+        template_promote_handler,
+    },
+    watchlist::{watchlist_create_handler, watchlist_screen_handler},
 };
 use crate::channels::web::test_support::*;
 use crate::db::{ConflictDecision, UserRole};
@@ -52,7 +129,7 @@ use crate::workspace::Workspace;
 use axum::{
     Json,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
 };
 use chrono::Utc;
 use regex::Regex;
@@ -1008,6 +1085,39 @@ async fn chat_send_includes_active_matter_metadata_when_setting_exists() {
     );
 }
 
+#[tokio::test]
+async fn chat_send_returns_429_with_retry_after_when_intake_queue_is_full() {
+    let state = minimal_test_gateway_state(None);
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    *state.msg_tx.write().await = Some(tx);
+
+    let send = |content: &str| {
+        chat_send_handler(
+            State(Arc::clone(&state)),
+            Json(SendMessageRequest {
+                content: content.to_string(),
+                thread_id: None,
+            }),
+        )
+    };
+    let (status, _) = send("first").await.expect("first message is admitted");
+    assert_eq!(status, StatusCode::ACCEPTED);
+
+    let rejected = send("second").await.expect_err("queue is full");
+    assert_eq!(rejected.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(
+        rejected
+            .headers()
+            .get(axum::http::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok()),
+        Some("5")
+    );
+
+    let stats = state.intake.stats(state.msg_tx.read().await.as_ref());
+    assert_eq!((stats.depth, stats.rejected_queue_full), (1, 1));
+    assert_eq!(rx.recv().await.expect("first message").content, "first");
+}
+
 #[cfg(feature = "libsql")]
 #[tokio::test]
 async fn chat_send_sets_active_matter_metadata_to_null_when_missing() {
@@ -1134,6 +1244,209 @@ async fn chat_new_thread_binds_active_matter_to_conversation() {
     assert_eq!(bound.as_deref(), Some("demo"));
 }
 
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn chat_thread_config_patch_persists_in_conversation_metadata() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    let state = test_gateway_state_with_store_and_workspace(Arc::clone(&db), workspace);
+    let thread_id = Uuid::new_v4();
+    db.ensure_conversation(thread_id, "gateway", "test-user", None)
+        .await
+        .expect("ensure conversation");
+    let patch = |system_prompt_additions, tone, enabled_tools| UpdateThreadConfigRequest {
+        system_prompt_additions,
+        tone,
+        enabled_tools,
+    };
+
+    let Json(resp) = chat_thread_config_patch_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(thread_id.to_string()),
+        Json(patch(
+            Some(Some(
+                "Cite a pinpoint for every authority; flag unverified citations.".to_string(),
+            )),
+            Some(Some("Formal".to_string())),
+            Some(Some(vec![
+                "memory_search".to_string(),
+                " draft_check ".to_string(),
+                "memory_search".to_string(),
+            ])),
+        )),
+    )
+    .await
+    .expect("patch config");
+    assert_eq!(
+        resp.config.tone,
+        Some(crate::agent::thread_config::ThreadTone::Formal)
+    );
+    assert_eq!(
+        resp.config.enabled_tools,
+        Some(vec!["draft_check".to_string(), "memory_search".to_string()])
+    );
+    let metadata = db
+        .get_conversation_metadata(thread_id)
+        .await
+        .expect("metadata")
+        .expect("metadata row");
+    assert_eq!(
+        crate::agent::thread_config::ThreadConfig::from_metadata(&metadata).as_ref(),
+        Some(&resp.config)
+    );
+
+    // Omitted fields stay; empty values clear.
+    let Json(resp) = chat_thread_config_patch_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(thread_id.to_string()),
+        Json(patch(
+            None,
+            Some(Some(" ".to_string())),
+            Some(Some(Vec::new())),
+        )),
+    )
+    .await
+    .expect("clear tone and tools");
+    assert_eq!(resp.config.tone, None);
+    assert_eq!(resp.config.enabled_tools, None);
+    let Json(fetched) =
+        chat_thread_config_handler(State(Arc::clone(&state)), Path(thread_id.to_string()))
+            .await
+            .expect("get config");
+    assert_eq!(
+        fetched.config.system_prompt_additions.as_deref(),
+        Some("Cite a pinpoint for every authority; flag unverified citations.")
+    );
+
+    let err = chat_thread_config_patch_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(thread_id.to_string()),
+        Json(patch(None, Some(Some("shouty".to_string())), None)),
+    )
+    .await
+    .expect_err("unknown tone");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    let err = chat_thread_config_handler(State(state), Path(Uuid::new_v4().to_string()))
+        .await
+        .expect_err("unknown thread");
+    assert_eq!(err.0, StatusCode::NOT_FOUND);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn chat_branch_copies_history_and_regenerates_in_new_thread() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    let state = test_gateway_state_with_store_and_workspace(Arc::clone(&db), workspace);
+    let source = Uuid::new_v4();
+    db.ensure_conversation(source, "gateway", "test-user", None)
+        .await
+        .expect("ensure conversation");
+    for (role, content) in [
+        ("user", "Draft a limitation of liability clause."),
+        ("assistant", "Draft A: liability capped at fees paid."),
+        ("user", "Make it mutual."),
+        ("assistant", "Draft B: mutual cap at fees paid."),
+        ("user", "Add a carve-out for confidentiality."),
+        (
+            "assistant",
+            "Draft C: mutual cap with confidentiality carve-out.",
+        ),
+    ] {
+        db.add_conversation_message(source, role, content)
+            .await
+            .expect("add message");
+    }
+    db.bind_conversation_to_matter(source, "test-user", "demo")
+        .await
+        .expect("bind matter");
+    db.update_conversation_metadata_field(
+        source,
+        "thread_config",
+        &serde_json::json!({"tone": "formal"}),
+    )
+    .await
+    .expect("set thread config");
+
+    let (status, Json(branch)) = chat_branch_thread_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(source.to_string()),
+        Json(BranchThreadRequest {
+            turn: Some(1),
+            regenerate: false,
+        }),
+    )
+    .await
+    .expect("branch");
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(branch.branched_from, source);
+    assert_eq!(branch.copied_turns, 2);
+    assert_eq!(branch.thread.matter_id.as_deref(), Some("demo"));
+    let copied: Vec<String> = db
+        .list_conversation_messages(branch.thread.id)
+        .await
+        .expect("branch messages")
+        .into_iter()
+        .map(|m| m.content)
+        .collect();
+    assert_eq!(copied.len(), 4);
+    assert_eq!(copied[3], "Draft B: mutual cap at fees paid.");
+    let metadata = db
+        .get_conversation_metadata(branch.thread.id)
+        .await
+        .expect("metadata")
+        .expect("metadata row");
+    assert_eq!(metadata["branched_from"]["thread_id"], source.to_string());
+    assert_eq!(metadata["thread_config"]["tone"], "formal");
+    assert_eq!(
+        db.list_conversation_messages(source)
+            .await
+            .expect("source messages")
+            .len(),
+        6
+    );
+
+    // Regenerating turn 1 keeps only turn 0 and asks turn 1 again.
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    *state.msg_tx.write().await = Some(tx);
+    let (_, Json(regenerated)) = chat_branch_thread_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(source.to_string()),
+        Json(BranchThreadRequest {
+            turn: Some(1),
+            regenerate: true,
+        }),
+    )
+    .await
+    .expect("regenerate");
+    assert_eq!(regenerated.copied_turns, 1);
+    let sent = rx.recv().await.expect("regeneration queued");
+    assert_eq!(Some(sent.id), regenerated.message_id);
+    assert_eq!(sent.content, "Make it mutual.");
+    assert_eq!(
+        sent.thread_id.as_deref(),
+        Some(regenerated.thread.id.to_string().as_str())
+    );
+
+    let err = chat_branch_thread_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(source.to_string()),
+        Json(BranchThreadRequest {
+            turn: Some(7),
+            regenerate: false,
+        }),
+    )
+    .await
+    .expect_err("unknown turn");
+    assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+}
+
 #[cfg(feature = "libsql")]
 #[tokio::test]
 async fn chat_threads_filter_returns_only_requested_matter() {
@@ -1257,6 +1570,7 @@ async fn matters_create_creates_scaffold_and_sets_active() {
             adversaries: vec!["Foo LLC".to_string()],
             conflict_decision: None,
             conflict_note: None,
+            custom_fields: None,
         }),
     )
     .await
@@ -1325,7 +1639,7 @@ async fn matters_create_creates_scaffold_and_sets_active() {
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matters_list_includes_optional_metadata_fields() {
+async fn matters_create_layers_practice_area_scaffold() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     let state =
@@ -1335,395 +1649,688 @@ async fn matters_list_includes_optional_metadata_fields() {
         State(Arc::clone(&state)),
         owner_principal(),
         Json(CreateMatterRequest {
-            matter_id: "Acme v. Foo".to_string(),
-            client: "Acme Corp".to_string(),
+            matter_id: "Smith Divorce".to_string(),
+            client: "Jane Smith".to_string(),
             confidentiality: "attorney-client-privileged".to_string(),
             retention: "follow-firm-policy".to_string(),
-            jurisdiction: Some("SDNY / Delaware".to_string()),
-            practice_area: Some("commercial litigation".to_string()),
-            opened_date: Some("2024-03-15".to_string()),
+            jurisdiction: None,
+            practice_area: Some("Family Law".to_string()),
+            opened_date: None,
             opened_at: None,
-            team: vec!["Lead Counsel".to_string()],
-            adversaries: vec!["Foo LLC".to_string()],
+            team: Vec::new(),
+            adversaries: vec!["John Smith".to_string()],
             conflict_decision: None,
             conflict_note: None,
+            custom_fields: None,
         }),
     )
     .await
-    .expect("create matter should succeed");
+    .expect("create family matter");
 
-    let Json(list) = matters_list_handler(State(state), owner_principal())
+    let checklist = workspace
+        .read("matters/smith-divorce/workflows/family_intake_checklist.md")
         .await
-        .expect("matters list should succeed");
-    assert_eq!(list.matters.len(), 1);
-    let matter = &list.matters[0];
-    assert_eq!(matter.id, "acme-v--foo");
-    assert_eq!(matter.jurisdiction.as_deref(), Some("SDNY / Delaware"));
-    assert_eq!(
-        matter.practice_area.as_deref(),
-        Some("commercial litigation")
+        .expect("family intake checklist should exist");
+    assert!(checklist.content.contains("date of separation"));
+    workspace
+        .read("matters/smith-divorce/financial/disclosure_tracker.md")
+        .await
+        .expect("disclosure tracker should exist");
+    workspace
+        .read("matters/smith-divorce/workflows/intake_checklist.md")
+        .await
+        .expect("base scaffold is still written");
+    let readme = workspace
+        .read("matters/smith-divorce/README.md")
+        .await
+        .expect("readme");
+    assert!(
+        readme
+            .content
+            .contains("Practice area scaffold: Family law")
+    );
+    assert!(
+        workspace
+            .read("matters/smith-divorce/medical/records_tracker.md")
+            .await
+            .is_err()
     );
-    assert_eq!(matter.opened_date.as_deref(), Some("2024-03-15"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matters_create_rejects_duplicate() {
+async fn matters_create_instantiates_firm_template_dir() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     let state =
         test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    for (path, content) in [
+        (
+            "matters/_template/matter.yaml",
+            "matter_id: example-matter\n",
+        ),
+        (
+            "matters/_template/workflows/intake_checklist.md",
+            "# Intake for {{ client.name }}\n\n- [ ] Engagement letter for {{ matter.matter_id }}\n",
+        ),
+        (
+            "matters/_template/discovery/request_tracker.md",
+            "{% if matter.practice_area != \"corporate\" %}# Discovery{% endif %}",
+        ),
+        (
+            "matters/_template/firm/billing_guidelines.md",
+            "{{ matter.client }} is billed under firm guidelines.",
+        ),
+    ] {
+        workspace.write(path, content).await.expect("seed template");
+    }
+    let request = |matter_id: &str| CreateMatterRequest {
+        matter_id: matter_id.to_string(),
+        client: "Acme Corp".to_string(),
+        confidentiality: "attorney-client-privileged".to_string(),
+        retention: "follow-firm-policy".to_string(),
+        jurisdiction: None,
+        practice_area: Some("corporate".to_string()),
+        opened_date: None,
+        opened_at: None,
+        team: Vec::new(),
+        adversaries: Vec::new(),
+        conflict_decision: None,
+        conflict_note: None,
+        custom_fields: None,
+    };
 
-    let _created = matters_create_handler(
+    let _ = matters_create_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(CreateMatterRequest {
-            matter_id: "demo".to_string(),
-            client: "Demo".to_string(),
-            confidentiality: "privileged".to_string(),
-            retention: "policy".to_string(),
-            jurisdiction: None,
-            practice_area: None,
-            opened_date: None,
-            opened_at: None,
-            team: vec![],
-            adversaries: vec![],
-            conflict_decision: None,
-            conflict_note: None,
-        }),
+        Json(request("Acme Financing")),
     )
     .await
-    .expect("first create should succeed");
+    .expect("create matter from firm template");
+
+    let checklist = workspace
+        .read("matters/acme-financing/workflows/intake_checklist.md")
+        .await
+        .expect("intake checklist");
+    assert_eq!(
+        checklist.content,
+        "# Intake for Acme Corp\n\n- [ ] Engagement letter for acme-financing\n"
+    );
+    let metadata = workspace
+        .read("matters/acme-financing/matter.yaml")
+        .await
+        .expect("matter.yaml");
+    assert!(metadata.content.contains("matter_id: acme-financing"));
+    assert!(
+        workspace
+            .read("matters/acme-financing/discovery/request_tracker.md")
+            .await
+            .is_err()
+    );
+    let guidelines = workspace
+        .read("matters/acme-financing/firm/billing_guidelines.md")
+        .await
+        .expect("firm-only template file");
+    assert_eq!(
+        guidelines.content,
+        "Acme Corp is billed under firm guidelines."
+    );
 
+    workspace
+        .write("matters/_template/broken.md", "{% if %}")
+        .await
+        .expect("seed broken template");
     let err = matters_create_handler(
-        State(state),
+        State(Arc::clone(&state)),
         owner_principal(),
         Json(CreateMatterRequest {
-            matter_id: "demo".to_string(),
-            client: "Demo".to_string(),
-            confidentiality: "privileged".to_string(),
-            retention: "policy".to_string(),
-            jurisdiction: None,
-            practice_area: None,
-            opened_date: None,
-            opened_at: None,
-            team: vec![],
-            adversaries: vec![],
-            conflict_decision: None,
-            conflict_note: None,
+            client: "Globex Inc".to_string(),
+            ..request("Globex Financing")
         }),
     )
     .await
-    .expect_err("duplicate should fail");
-
-    assert_eq!(err.0, StatusCode::CONFLICT);
-    assert!(err.1.contains("already exists"));
+    .expect_err("broken template rejects the request");
+    assert_eq!(err.0, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(err.1.contains("broken.md"));
+    assert!(
+        db.get_matter_db("test-user", "globex-financing")
+            .await
+            .expect("lookup")
+            .is_none()
+    );
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matters_create_rejects_invalid_opened_date() {
+async fn matter_stage_transitions_follow_workflow_rules() {
+    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
+    crate::legal::audit::clear_test_events();
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    let state = test_gateway_state_with_store_and_workspace(db, workspace);
-
-    let err = matters_create_handler(
-        State(state),
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    let _ = matters_create_handler(
+        State(Arc::clone(&state)),
         owner_principal(),
         Json(CreateMatterRequest {
-            matter_id: "demo".to_string(),
-            client: "Demo".to_string(),
-            confidentiality: "privileged".to_string(),
-            retention: "policy".to_string(),
+            matter_id: "Acme v. Foo".to_string(),
+            client: "Acme Corp".to_string(),
+            confidentiality: "attorney-client-privileged".to_string(),
+            retention: "follow-firm-policy".to_string(),
             jurisdiction: None,
-            practice_area: None,
-            opened_date: Some("03/15/2024".to_string()),
+            practice_area: Some("commercial litigation".to_string()),
+            opened_date: None,
             opened_at: None,
-            team: vec![],
-            adversaries: vec![],
+            team: Vec::new(),
+            adversaries: vec!["Foo LLC".to_string()],
             conflict_decision: None,
             conflict_note: None,
+            custom_fields: None,
         }),
     )
     .await
-    .expect_err("invalid opened_date should fail");
-
-    assert_eq!(err.0, StatusCode::BAD_REQUEST);
-    assert!(err.1.contains("YYYY-MM-DD"));
-}
-
-#[cfg(feature = "libsql")]
-#[tokio::test]
-async fn matters_create_rejects_overlong_optional_metadata_fields() {
-    let (db, _tmp) = crate::testing::test_db().await;
-    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    let state = test_gateway_state_with_store_and_workspace(db, workspace);
+    .expect("create matter");
+    let transition = |stage: &str| {
+        matter_stage_transition_handler(
+            State(Arc::clone(&state)),
+            owner_principal(),
+            Path("acme-v--foo".to_string()),
+            Json(MatterStageTransitionRequest {
+                stage: stage.to_string(),
+                note: None,
+            }),
+        )
+    };
 
-    let err = matters_create_handler(
-        State(state),
+    let Json(info) = matter_stage_get_handler(
+        State(Arc::clone(&state)),
         owner_principal(),
-        Json(CreateMatterRequest {
-            matter_id: "demo".to_string(),
-            client: "Demo".to_string(),
-            confidentiality: "privileged".to_string(),
-            retention: "policy".to_string(),
-            jurisdiction: Some("X".repeat(257)),
-            practice_area: None,
-            opened_date: None,
-            opened_at: None,
-            team: vec![],
-            adversaries: vec![],
-            conflict_decision: None,
-            conflict_note: None,
-        }),
+        Path("acme-v--foo".to_string()),
     )
     .await
-    .expect_err("overlong jurisdiction should fail");
+    .expect("stage info");
+    assert_eq!(info.workflow_id, "litigation");
+    assert_eq!(info.stage, None);
+    let next: Vec<_> = info.next.iter().map(|stage| stage.id.as_str()).collect();
+    assert_eq!(next, vec!["intake", "pleadings", "settlement"]);
+    assert!(info.next[1].missing[0].contains("intake_checklist.md"));
 
+    let err = transition("trial").await.expect_err("not reachable yet");
+    assert_eq!(err.0, StatusCode::CONFLICT);
+    let err = transition("appeal").await.expect_err("unknown stage");
     assert_eq!(err.0, StatusCode::BAD_REQUEST);
-    assert!(err.1.contains("jurisdiction"));
-    assert!(err.1.contains("at most"));
-}
-
+    let err = transition("pleadings")
+        .await
+        .expect_err("intake checklist is still open");
+    assert_eq!(err.0, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let Json(moved) = transition("intake").await.expect("enter intake");
+    assert_eq!(moved.matter.stage.as_deref(), Some("intake"));
+    assert_eq!(moved.created_tasks.len(), 1);
+
+    let checklist_path = "matters/acme-v--foo/workflows/intake_checklist.md";
+    let checklist = workspace.read(checklist_path).await.expect("checklist");
+    workspace
+        .write(checklist_path, &checklist.content.replace("- [ ]", "- [x]"))
+        .await
+        .expect("complete checklist");
+    let Json(moved) = transition("pleadings").await.expect("enter pleadings");
+    assert_eq!(moved.from.as_deref(), Some("intake"));
+    assert_eq!(moved.to, "pleadings");
+    let titles: Vec<_> = moved
+        .created_tasks
+        .iter()
+        .map(|task| task.title.as_str())
+        .collect();
+    assert_eq!(
+        titles,
+        vec![
+            "Draft initial pleading",
+            "Calendar service and response deadlines"
+        ]
+    );
+
+    let err = matter_patch_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("acme-v--foo".to_string()),
+        Json(serde_json::from_value(serde_json::json!({ "stage": "trial" })).unwrap()),
+    )
+    .await
+    .expect_err("PATCH cannot skip the workflow");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+
+    let events = crate::legal::audit::test_events_snapshot();
+    assert_eq!(
+        events
+            .iter()
+            .filter(|event| event.event_type == "matter_stage_changed")
+            .count(),
+        2
+    );
+}
+
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matters_create_rejects_empty_after_sanitize() {
+async fn matter_close_requires_clean_checks_and_starts_retention_clock() {
+    use crate::db::{CreateMatterDeadlineParams, MatterDeadlineType, UpdateMatterDeadlineParams};
+
+    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
+    crate::legal::audit::clear_test_events();
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    let state = test_gateway_state_with_store_and_workspace(db, workspace);
-
-    let err = matters_create_handler(
-        State(state),
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    let _ = matters_create_handler(
+        State(Arc::clone(&state)),
         owner_principal(),
         Json(CreateMatterRequest {
-            matter_id: "!!!".to_string(),
-            client: "Demo".to_string(),
-            confidentiality: "privileged".to_string(),
-            retention: "policy".to_string(),
+            matter_id: "Acme v. Foo".to_string(),
+            client: "Acme Corp".to_string(),
+            confidentiality: "attorney-client-privileged".to_string(),
+            retention: "retain-10-years".to_string(),
             jurisdiction: None,
             practice_area: None,
             opened_date: None,
             opened_at: None,
-            team: vec![],
-            adversaries: vec![],
+            team: Vec::new(),
+            adversaries: vec!["Foo LLC".to_string()],
             conflict_decision: None,
             conflict_note: None,
+            custom_fields: None,
         }),
     )
     .await
-    .expect_err("invalid matter id should fail");
+    .expect("create matter");
+    let deadline = db
+        .create_matter_deadline(
+            "test-user",
+            "acme-v--foo",
+            &CreateMatterDeadlineParams {
+                title: "Reply brief".to_string(),
+                deadline_type: MatterDeadlineType::CourtDate,
+                due_at: Utc::now() + chrono::Duration::days(10),
+                completed_at: None,
+                reminder_days: Vec::new(),
+                rule_ref: None,
+                computed_from: None,
+                task_id: None,
+                explanation: None,
+                rule_version: None,
+                is_unsupported: false,
+            },
+        )
+        .await
+        .expect("create deadline");
 
-    assert_eq!(err.0, StatusCode::BAD_REQUEST);
-    assert!(err.1.contains("empty after sanitization"));
-}
+    let Json(status) = matter_closeout_status_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("acme-v--foo".to_string()),
+    )
+    .await
+    .expect("closeout status");
+    assert!(!status.ready);
+    assert_eq!(status.retention_years, 10);
+    let open = status
+        .checks
+        .iter()
+        .find(|check| check.id == "open_deadlines")
+        .expect("deadline check");
+    assert!(!open.passed);
+    assert!(open.detail.contains("Reply brief"));
 
-#[cfg(feature = "libsql")]
-#[tokio::test]
-async fn intake_conflict_check_returns_structured_hits() {
-    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
-    crate::legal::audit::clear_test_events();
-    let (db, _tmp) = crate::testing::test_db().await;
-    db.seed_matter_parties("existing-matter", "Acme Corp", &[], None)
-        .await
-        .expect("seed matter parties");
-    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    let mut legal = test_legal_config();
-    legal.enabled = true;
-    legal.require_matter_context = false;
-    legal.conflict_check_enabled = true;
-    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
+    let err = matter_close_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("acme-v--foo".to_string()),
+        Json(CloseMatterRequest::default()),
+    )
+    .await
+    .expect_err("open deadline blocks closing");
+    assert_eq!(err.0, StatusCode::CONFLICT);
+    assert!(err.1.contains("Reply brief"));
 
-    let Json(resp) = matters_conflict_check_handler(
-        State(state),
-        Json(MatterIntakeConflictCheckRequest {
-            matter_id: "new-matter".to_string(),
-            client_names: vec!["Acme Corp".to_string()],
-            adversary_names: vec!["Other Party".to_string()],
+    db.update_matter_deadline(
+        "test-user",
+        "acme-v--foo",
+        deadline.id,
+        &UpdateMatterDeadlineParams {
+            title: None,
+            deadline_type: None,
+            due_at: None,
+            completed_at: Some(Some(Utc::now())),
+            reminder_days: None,
+            rule_ref: None,
+            computed_from: None,
+            task_id: None,
+            explanation: None,
+            rule_version: None,
+            is_unsupported: None,
+        },
+    )
+    .await
+    .expect("complete deadline")
+    .expect("deadline exists");
+
+    let Json(closed) = matter_close_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("acme-v--foo".to_string()),
+        Json(CloseMatterRequest {
+            note: Some("File reviewed by lead".to_string()),
+            retention_years: None,
         }),
     )
     .await
-    .expect("intake conflict check should succeed");
+    .expect("close matter");
+    assert_eq!(closed.matter.status.as_deref(), Some("closed"));
+    assert!(closed.checks.iter().all(|check| check.passed));
+    let letter = workspace
+        .read(&closed.closing_letter_path)
+        .await
+        .expect("closing letter written");
+    assert!(letter.content.contains("Dear Acme Corp"));
+    let report = workspace
+        .read(&closed.closeout_report_path)
+        .await
+        .expect("closeout record written");
+    assert!(report.content.contains("File reviewed by lead"));
 
-    assert!(resp.matched);
-    assert_eq!(resp.matter_id, "new-matter");
-    assert_eq!(resp.checked_parties.len(), 2);
-    assert!(!resp.hits.is_empty());
-    assert!(resp.hits.iter().any(|hit| hit.party == "Acme Corp"));
+    let deadlines = db
+        .list_matter_deadlines("test-user", "acme-v--foo")
+        .await
+        .expect("list deadlines");
+    let retention = deadlines
+        .iter()
+        .find(|d| d.title == crate::legal::closeout::RETENTION_DEADLINE_TITLE)
+        .expect("retention deadline");
+    assert_eq!(Some(retention.id.to_string()), closed.retention_deadline_id);
+    assert_eq!(retention.due_at.to_rfc3339(), closed.retain_until);
+
+    let err = matter_close_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("acme-v--foo".to_string()),
+        Json(CloseMatterRequest::default()),
+    )
+    .await
+    .expect_err("already closed");
+    assert_eq!(err.0, StatusCode::CONFLICT);
 
     let events = crate::legal::audit::test_events_snapshot();
-    let intake_event = events
-        .iter()
-        .find(|event| event.event_type == "matter_intake_conflict_check")
-        .expect("expected intake conflict audit event");
-    assert_eq!(intake_event.details["matched"], true);
-    assert_eq!(intake_event.details["matter_id"], "new-matter");
+    assert!(events.iter().any(|event| {
+        event.event_type == "matter_closed"
+            && event.details["source"] == "closeout"
+            && event.details["retention_years"] == 10
+    }));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn intake_conflict_check_rejects_empty_client_names() {
+async fn inbound_email_is_filed_by_plus_address_and_subject_tag() {
+    use base64::Engine as _;
+
+    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
+    crate::legal::audit::clear_test_events();
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    let state = test_gateway_state_with_store_and_workspace(db, workspace);
-
-    let err = matters_conflict_check_handler(
-        State(state),
-        Json(MatterIntakeConflictCheckRequest {
-            matter_id: "new-matter".to_string(),
-            client_names: vec!["   ".to_string()],
-            adversary_names: vec![],
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    let _ = matters_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(CreateMatterRequest {
+            matter_id: "Acme v. Foo".to_string(),
+            client: "Acme Corp".to_string(),
+            confidentiality: "attorney-client-privileged".to_string(),
+            retention: "follow-firm-policy".to_string(),
+            jurisdiction: None,
+            practice_area: None,
+            opened_date: None,
+            opened_at: None,
+            team: Vec::new(),
+            adversaries: vec!["Foo LLC".to_string()],
+            conflict_decision: None,
+            conflict_note: None,
+            custom_fields: None,
         }),
     )
     .await
-    .expect_err("empty client list should fail");
+    .expect("create matter");
+    let token = "inbound-token-0123456789abcdef";
+    db.set_setting(
+        "test-user",
+        crate::legal::email_intake::EMAIL_INTAKE_SETTING_KEY,
+        &serde_json::json!({ "token": token, "domains": ["firm.example"] }),
+    )
+    .await
+    .expect("configure email intake");
+    let email =
+        |to: &str, subject: &str, attachments: Vec<InboundEmailAttachment>| InboundEmailRequest {
+            from: "Jane Client <jane@acme.example>".to_string(),
+            to: vec![to.to_string()],
+            cc: Vec::new(),
+            recipients: Vec::new(),
+            subject: subject.to_string(),
+            text: "Please see attached.".to_string(),
+            message_id: None,
+            attachments,
+        };
 
-    assert_eq!(err.0, StatusCode::BAD_REQUEST);
-    assert!(err.1.contains("client_names"));
-}
+    let err = email_inbound_handler(
+        State(Arc::clone(&state)),
+        Path("wrong-token".to_string()),
+        Json(email(
+            "intake+acme-v--foo@firm.example",
+            "Lease",
+            Vec::new(),
+        )),
+    )
+    .await
+    .expect_err("unknown token");
+    assert_eq!(err.0, StatusCode::NOT_FOUND);
 
-#[cfg(feature = "libsql")]
-#[tokio::test]
-async fn intake_conflict_check_rejects_excessive_client_names() {
-    let (db, _tmp) = crate::testing::test_db().await;
-    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    let state = test_gateway_state_with_store_and_workspace(db, workspace);
-    let client_names: Vec<String> = (0..=MAX_INTAKE_CONFLICT_PARTIES)
-        .map(|idx| format!("Client {idx}"))
-        .collect();
+    let Json(filed) = email_inbound_handler(
+        State(Arc::clone(&state)),
+        Path(token.to_string()),
+        Json(email(
+            "Intake <intake+acme-v--foo@firm.example>",
+            "Signed lease",
+            vec![
+                InboundEmailAttachment {
+                    filename: "lease.txt".to_string(),
+                    content_type: Some("text/plain".to_string()),
+                    content: base64::engine::general_purpose::STANDARD
+                        .encode("This lease agreement is made between Acme Corp and Foo LLC."),
+                },
+                InboundEmailAttachment {
+                    filename: "scan.pdf".to_string(),
+                    content_type: Some("application/pdf".to_string()),
+                    content: base64::engine::general_purpose::STANDARD.encode([0xff, 0xfe, 0x00]),
+                },
+            ],
+        )),
+    )
+    .await
+    .expect("file by plus address");
+    assert!(filed.filed);
+    assert_eq!(filed.matter_id.as_deref(), Some("acme-v--foo"));
+    assert!(
+        filed
+            .message_path
+            .starts_with("matters/acme-v--foo/communications/inbox/")
+    );
+    assert_eq!(filed.attachments.len(), 1);
+    assert!(filed.attachments[0].starts_with("matters/acme-v--foo/"));
+    assert!(filed.skipped_attachments[0].starts_with("scan.pdf (not plain text"));
+    let message = workspace
+        .read(&filed.message_path)
+        .await
+        .expect("message filed");
+    assert!(
+        message
+            .content
+            .contains("- **Attachment not filed:** scan.pdf")
+    );
+    let log = workspace
+        .read("matters/acme-v--foo/communications/contact_log.md")
+        .await
+        .expect("contact log");
+    assert!(log.content.contains("Received \"Signed lease\""));
+    let documents = db
+        .list_matter_documents_db("test-user", "acme-v--foo")
+        .await
+        .expect("matter documents");
+    assert!(documents.iter().any(|doc| doc.path == filed.message_path));
+    assert!(documents.iter().any(|doc| doc.path == filed.attachments[0]));
 
-    let err = matters_conflict_check_handler(
-        State(state),
-        Json(MatterIntakeConflictCheckRequest {
-            matter_id: "new-matter".to_string(),
-            client_names,
-            adversary_names: vec![],
-        }),
+    let Json(tagged) = email_inbound_handler(
+        State(Arc::clone(&state)),
+        Path(token.to_string()),
+        Json(email(
+            "reception@firm.example",
+            "Re: Disclosure [Matter: Acme v. Foo]",
+            Vec::new(),
+        )),
     )
     .await
-    .expect_err("oversized client list should fail");
+    .expect("file by subject tag");
+    assert_eq!(tagged.matter_id.as_deref(), Some("acme-v--foo"));
 
-    assert_eq!(err.0, StatusCode::BAD_REQUEST);
-    assert!(err.1.contains("client_names"));
-    assert!(err.1.contains("at most"));
+    let Json(unfiled) = email_inbound_handler(
+        State(Arc::clone(&state)),
+        Path(token.to_string()),
+        Json(email(
+            "intake+unknown-matter@firm.example",
+            "Question",
+            Vec::new(),
+        )),
+    )
+    .await
+    .expect("unmatched mail is kept");
+    assert!(!unfiled.filed);
+    assert!(unfiled.message_path.starts_with("email/unfiled/"));
+
+    let events = crate::legal::audit::test_events_snapshot();
+    assert_eq!(
+        events
+            .iter()
+            .filter(|event| event.event_type == "email_filed")
+            .count(),
+        2
+    );
+    assert!(
+        events
+            .iter()
+            .any(|event| event.event_type == "email_unfiled")
+    );
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn intake_conflict_check_respects_disabled_policy() {
+async fn matters_list_includes_optional_metadata_fields() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    let mut legal = test_legal_config();
-    legal.enabled = true;
-    legal.conflict_check_enabled = false;
-    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
 
-    let err = matters_conflict_check_handler(
-        State(state),
-        Json(MatterIntakeConflictCheckRequest {
-            matter_id: "new-matter".to_string(),
-            client_names: vec!["Acme Corp".to_string()],
-            adversary_names: vec![],
+    let _ = matters_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(CreateMatterRequest {
+            matter_id: "Acme v. Foo".to_string(),
+            client: "Acme Corp".to_string(),
+            confidentiality: "attorney-client-privileged".to_string(),
+            retention: "follow-firm-policy".to_string(),
+            jurisdiction: Some("SDNY / Delaware".to_string()),
+            practice_area: Some("commercial litigation".to_string()),
+            opened_date: Some("2024-03-15".to_string()),
+            opened_at: None,
+            team: vec!["Lead Counsel".to_string()],
+            adversaries: vec!["Foo LLC".to_string()],
+            conflict_decision: None,
+            conflict_note: None,
+            custom_fields: None,
         }),
     )
     .await
-    .expect_err("disabled policy should reject");
+    .expect("create matter should succeed");
 
-    assert_eq!(err.0, StatusCode::CONFLICT);
-    assert!(err.1.contains("disabled"));
+    let Json(list) = matters_list_handler(State(state), owner_principal())
+        .await
+        .expect("matters list should succeed");
+    assert_eq!(list.matters.len(), 1);
+    let matter = &list.matters[0];
+    assert_eq!(matter.id, "acme-v--foo");
+    assert_eq!(matter.jurisdiction.as_deref(), Some("SDNY / Delaware"));
+    assert_eq!(
+        matter.practice_area.as_deref(),
+        Some("commercial litigation")
+    );
+    assert_eq!(matter.opened_date.as_deref(), Some("2024-03-15"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn conflicts_reindex_backfills_graph_and_emits_audit_event() {
-    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
-    crate::legal::audit::clear_test_events();
+async fn matters_create_rejects_duplicate() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    workspace
-        .write(
-            "matters/demo/matter.yaml",
-            r#"
-matter_id: demo
-client: Demo Client
-team:
-  - Lead Counsel
-confidentiality: attorney-client-privileged
-adversaries:
-  - Foo Industries
-retention: follow-firm-policy
-opened_at: 2026-02-28
-"#,
-        )
-        .await
-        .expect("seed matter metadata");
-    workspace
-        .write(
-            "conflicts.json",
-            r#"[{"name":"Example Adverse Party","aliases":["Example Co"]}]"#,
-        )
-        .await
-        .expect("seed conflicts");
-
-    let mut legal = test_legal_config();
-    legal.enabled = true;
-    legal.conflict_check_enabled = true;
-    let state = test_gateway_state_with_store_workspace_and_legal(
-        Arc::clone(&db),
-        Arc::clone(&workspace),
-        legal,
-    );
-
-    let Json(resp) = matters_conflicts_reindex_handler(State(state))
-        .await
-        .expect("reindex should succeed");
-
-    assert_eq!(resp.status, "ok");
-    assert_eq!(resp.report.seeded_matters, 1);
-    assert_eq!(resp.report.global_conflicts_seeded, 1);
-    assert_eq!(resp.report.global_aliases_seeded, 1);
-
-    let alias_hits = db
-        .find_conflict_hits_for_names(&["Example Co".to_string()], 20)
-        .await
-        .expect("query seeded alias");
-    assert!(
-        alias_hits
-            .iter()
-            .any(|hit| { hit.matter_id == crate::legal::matter::GLOBAL_CONFLICT_GRAPH_MATTER_ID })
-    );
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
 
-    let events = crate::legal::audit::test_events_snapshot();
-    assert!(events.iter().any(|event| {
-        event.event_type == "conflict_graph_reindexed"
-            && event.details["seeded_matters"] == 1
-            && event.details["global_conflicts_seeded"] == 1
-    }));
-}
+    let _created = matters_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(CreateMatterRequest {
+            matter_id: "demo".to_string(),
+            client: "Demo".to_string(),
+            confidentiality: "privileged".to_string(),
+            retention: "policy".to_string(),
+            jurisdiction: None,
+            practice_area: None,
+            opened_date: None,
+            opened_at: None,
+            team: vec![],
+            adversaries: vec![],
+            conflict_decision: None,
+            conflict_note: None,
+            custom_fields: None,
+        }),
+    )
+    .await
+    .expect("first create should succeed");
 
-#[cfg(feature = "libsql")]
-#[tokio::test]
-async fn conflicts_reindex_respects_disabled_policy() {
-    let (db, _tmp) = crate::testing::test_db().await;
-    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    let mut legal = test_legal_config();
-    legal.enabled = true;
-    legal.conflict_check_enabled = false;
-    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
+    let err = matters_create_handler(
+        State(state),
+        owner_principal(),
+        Json(CreateMatterRequest {
+            matter_id: "demo".to_string(),
+            client: "Demo".to_string(),
+            confidentiality: "privileged".to_string(),
+            retention: "policy".to_string(),
+            jurisdiction: None,
+            practice_area: None,
+            opened_date: None,
+            opened_at: None,
+            team: vec![],
+            adversaries: vec![],
+            conflict_decision: None,
+            conflict_note: None,
+            custom_fields: None,
+        }),
+    )
+    .await
+    .expect_err("duplicate should fail");
 
-    let err = matters_conflicts_reindex_handler(State(state))
-        .await
-        .expect_err("disabled policy should reject reindex");
     assert_eq!(err.0, StatusCode::CONFLICT);
-    assert!(err.1.contains("disabled"));
+    assert!(err.1.contains("already exists"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matters_create_requires_conflict_decision_when_hits_exist() {
+async fn matters_create_rejects_invalid_opened_date() {
     let (db, _tmp) = crate::testing::test_db().await;
-    db.seed_matter_parties("existing-matter", "Acme Corp", &[], None)
-        .await
-        .expect("seed matter parties");
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     let state = test_gateway_state_with_store_and_workspace(db, workspace);
 
@@ -1731,2412 +2338,7344 @@ async fn matters_create_requires_conflict_decision_when_hits_exist() {
         State(state),
         owner_principal(),
         Json(CreateMatterRequest {
-            matter_id: "new-matter".to_string(),
-            client: "Acme Corp".to_string(),
+            matter_id: "demo".to_string(),
+            client: "Demo".to_string(),
             confidentiality: "privileged".to_string(),
             retention: "policy".to_string(),
             jurisdiction: None,
             practice_area: None,
-            opened_date: None,
+            opened_date: Some("03/15/2024".to_string()),
             opened_at: None,
             team: vec![],
             adversaries: vec![],
             conflict_decision: None,
             conflict_note: None,
+            custom_fields: None,
         }),
     )
     .await
-    .expect_err("missing conflict decision should fail");
+    .expect_err("invalid opened_date should fail");
 
-    assert_eq!(err.0, StatusCode::CONFLICT);
-    let body: serde_json::Value =
-        serde_json::from_str(&err.1).expect("conflict body should be json");
-    assert_eq!(body["conflict_required"], true);
-    assert!(body["hits"].as_array().is_some_and(|hits| !hits.is_empty()));
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    assert!(err.1.contains("YYYY-MM-DD"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matters_create_declined_records_audit_and_blocks_creation() {
-    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
-    crate::legal::audit::clear_test_events();
+async fn matters_create_rejects_overlong_optional_metadata_fields() {
     let (db, _tmp) = crate::testing::test_db().await;
-    db.seed_matter_parties("existing-matter", "Acme Corp", &[], None)
-        .await
-        .expect("seed matter parties");
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    let state = test_gateway_state_with_store_and_workspace(db, workspace);
 
     let err = matters_create_handler(
         State(state),
         owner_principal(),
         Json(CreateMatterRequest {
-            matter_id: "new-matter".to_string(),
-            client: "Acme Corp".to_string(),
+            matter_id: "demo".to_string(),
+            client: "Demo".to_string(),
             confidentiality: "privileged".to_string(),
             retention: "policy".to_string(),
-            jurisdiction: None,
+            jurisdiction: Some("X".repeat(257)),
             practice_area: None,
             opened_date: None,
             opened_at: None,
             team: vec![],
             adversaries: vec![],
-            conflict_decision: Some(ConflictDecision::Declined),
-            conflict_note: Some("Escalated to conflicts counsel".to_string()),
+            conflict_decision: None,
+            conflict_note: None,
+            custom_fields: None,
         }),
     )
     .await
-    .expect_err("declined decision should block creation");
-
-    assert_eq!(err.0, StatusCode::CONFLICT);
-    let body: serde_json::Value =
-        serde_json::from_str(&err.1).expect("declined body should be json");
-    assert_eq!(body["decision"], "declined");
-    let created = workspace.read("matters/new-matter/matter.yaml").await;
-    assert!(matches!(
-        created,
-        Err(crate::error::WorkspaceError::DocumentNotFound { .. })
-    ));
+    .expect_err("overlong jurisdiction should fail");
 
-    let events = crate::legal::audit::test_events_snapshot();
-    let decision_event = events
-        .iter()
-        .find(|event| event.event_type == "conflict_clearance_decision")
-        .expect("expected conflict_clearance_decision event");
-    assert_eq!(decision_event.details["decision"], "declined");
-    assert_eq!(decision_event.details["source"], "create_flow");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    assert!(err.1.contains("jurisdiction"));
+    assert!(err.1.contains("at most"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matters_create_waived_records_and_proceeds() {
-    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
-    crate::legal::audit::clear_test_events();
+async fn matters_create_rejects_empty_after_sanitize() {
     let (db, _tmp) = crate::testing::test_db().await;
-    db.seed_matter_parties("existing-matter", "Acme Corp", &[], None)
-        .await
-        .expect("seed matter parties");
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    let state = test_gateway_state_with_store_and_workspace(db, workspace);
 
-    let (status, Json(resp)) = matters_create_handler(
+    let err = matters_create_handler(
         State(state),
         owner_principal(),
         Json(CreateMatterRequest {
-            matter_id: "new-matter".to_string(),
-            client: "Acme Corp".to_string(),
+            matter_id: "!!!".to_string(),
+            client: "Demo".to_string(),
             confidentiality: "privileged".to_string(),
             retention: "policy".to_string(),
             jurisdiction: None,
             practice_area: None,
-            opened_date: Some("2026-02-28".to_string()),
+            opened_date: None,
             opened_at: None,
             team: vec![],
-            adversaries: vec!["Other Party".to_string()],
-            conflict_decision: Some(ConflictDecision::Waived),
-            conflict_note: Some("Waived after documented informed consent".to_string()),
+            adversaries: vec![],
+            conflict_decision: None,
+            conflict_note: None,
+            custom_fields: None,
         }),
     )
     .await
-    .expect("waived decision should allow creation");
-
-    assert_eq!(status, StatusCode::CREATED);
-    assert_eq!(resp.matter.id, "new-matter");
-    workspace
-        .read("matters/new-matter/matter.yaml")
-        .await
-        .expect("matter yaml should exist");
-
-    let hits = db
-        .find_conflict_hits_for_names(&["Acme Corp".to_string()], 20)
-        .await
-        .expect("conflict search should succeed");
-    assert!(
-        hits.iter().any(|hit| hit.matter_id == "new-matter"),
-        "seed_matter_parties should register new matter parties"
-    );
+    .expect_err("invalid matter id should fail");
 
-    let events = crate::legal::audit::test_events_snapshot();
-    assert!(events.iter().any(|event| {
-        event.event_type == "conflict_clearance_decision" && event.details["decision"] == "waived"
-    }));
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    assert!(err.1.contains("empty after sanitization"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matter_conflict_report_and_clearance_round_trip() {
+async fn intake_conflict_check_returns_structured_hits() {
+    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
+    crate::legal::audit::clear_test_events();
     let (db, _tmp) = crate::testing::test_db().await;
     db.seed_matter_parties("existing-matter", "Acme Corp", &[], None)
         .await
-        .expect("seed existing matter parties");
+        .expect("seed matter parties");
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
-        .await
-        .expect("sync matter row");
+    let mut legal = test_legal_config();
+    legal.enabled = true;
+    legal.require_matter_context = false;
+    legal.conflict_check_enabled = true;
+    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
 
-    let (_status, Json(party)) = matter_parties_upsert_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-        Json(UpsertMatterPartyRequest {
-            name: "Acme Corp".to_string(),
-            role: "adverse".to_string(),
-            aliases: vec!["Acme Corporation".to_string()],
-            notes: Some("Named defendant".to_string()),
-            opened_at: None,
-            closed_at: None,
+    let Json(resp) = matters_conflict_check_handler(
+        State(state),
+        Json(MatterIntakeConflictCheckRequest {
+            matter_id: "new-matter".to_string(),
+            client_names: vec!["Acme Corp".to_string()],
+            adversary_names: vec!["Other Party".to_string()],
         }),
     )
     .await
-    .expect("upsert adverse party");
-    assert_eq!(party.role, "adverse");
+    .expect("intake conflict check should succeed");
 
-    let Json(report) = matter_conflicts_report_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-    )
-    .await
-    .expect("conflict report should succeed");
-    assert!(
-        report
-            .hits
-            .iter()
-            .any(|hit| hit.matter_id == "existing-matter")
-    );
-    assert!(report.latest_clearance.is_none());
+    assert!(resp.matched);
+    assert_eq!(resp.matter_id, "new-matter");
+    assert_eq!(resp.checked_parties.len(), 2);
+    assert!(!resp.hits.is_empty());
+    assert!(resp.hits.iter().any(|hit| hit.party == "Acme Corp"));
 
-    let Json(clearance) = matter_conflicts_clearance_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-        Json(MatterConflictClearanceRequest {
-            decision: ConflictDecision::Waived,
-            note: Some("Waived after attorney review".to_string()),
-            reviewing_attorney: Some("Lead".to_string()),
+    let events = crate::legal::audit::test_events_snapshot();
+    let intake_event = events
+        .iter()
+        .find(|event| event.event_type == "matter_intake_conflict_check")
+        .expect("expected intake conflict audit event");
+    assert_eq!(intake_event.details["matched"], true);
+    assert_eq!(intake_event.details["matter_id"], "new-matter");
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn intake_conflict_check_rejects_empty_client_names() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    let state = test_gateway_state_with_store_and_workspace(db, workspace);
+
+    let err = matters_conflict_check_handler(
+        State(state),
+        Json(MatterIntakeConflictCheckRequest {
+            matter_id: "new-matter".to_string(),
+            client_names: vec!["   ".to_string()],
+            adversary_names: vec![],
         }),
     )
     .await
-    .expect("clearance should be recorded");
-    assert_eq!(clearance.decision, "waived");
-    assert!(clearance.hit_count >= 1);
-    assert_eq!(
-        clearance.latest_clearance.reviewing_attorney.as_deref(),
-        Some("Lead")
-    );
+    .expect_err("empty client list should fail");
 
-    let Json(report_after) = matter_conflicts_report_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-    )
-    .await
-    .expect("report after clearance should succeed");
-    assert_eq!(
-        report_after
-            .latest_clearance
-            .as_ref()
-            .expect("latest clearance should exist")
-            .decision,
-        "waived"
-    );
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    assert!(err.1.contains("client_names"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matters_create_rejects_excessive_adversaries() {
+async fn intake_conflict_check_rejects_excessive_client_names() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     let state = test_gateway_state_with_store_and_workspace(db, workspace);
-    let adversaries: Vec<String> = (0..=MAX_INTAKE_CONFLICT_PARTIES)
-        .map(|idx| format!("Adverse Party {idx}"))
+    let client_names: Vec<String> = (0..=MAX_INTAKE_CONFLICT_PARTIES)
+        .map(|idx| format!("Client {idx}"))
         .collect();
 
-    let err = matters_create_handler(
+    let err = matters_conflict_check_handler(
         State(state),
-        owner_principal(),
-        Json(CreateMatterRequest {
+        Json(MatterIntakeConflictCheckRequest {
             matter_id: "new-matter".to_string(),
-            client: "Acme Corp".to_string(),
-            confidentiality: "privileged".to_string(),
-            retention: "policy".to_string(),
-            jurisdiction: None,
-            practice_area: None,
-            opened_date: None,
-            opened_at: None,
-            team: vec![],
-            adversaries,
-            conflict_decision: None,
-            conflict_note: None,
+            client_names,
+            adversary_names: vec![],
         }),
     )
     .await
-    .expect_err("oversized adversary list should fail");
+    .expect_err("oversized client list should fail");
 
     assert_eq!(err.0, StatusCode::BAD_REQUEST);
-    assert!(err.1.contains("adversaries"));
+    assert!(err.1.contains("client_names"));
     assert!(err.1.contains("at most"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn conflicts_check_returns_hit_for_matching_entry() {
-    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
-    crate::legal::audit::clear_test_events();
-    crate::legal::matter::reset_conflict_cache_for_tests();
+async fn bulk_conflict_check_screens_long_lists_and_downloads_a_report() {
     let (db, _tmp) = crate::testing::test_db().await;
+    db.seed_matter_parties(
+        "existing-matter",
+        "Acme Corp",
+        &["Globex".to_string()],
+        None,
+    )
+    .await
+    .expect("seed matter parties");
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    workspace
-        .write(
-            "conflicts.json",
-            r#"[{"name":"Alpha Holdings","aliases":["Alpha"]}]"#,
-        )
-        .await
-        .expect("seed conflicts");
-
     let mut legal = test_legal_config();
     legal.enabled = true;
-    legal.require_matter_context = false;
     legal.conflict_check_enabled = true;
     let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
 
-    let Json(resp) = matters_conflicts_check_handler(
-        State(state),
-        Json(MatterConflictCheckRequest {
-            text: "Draft strategy for Alpha Holdings".to_string(),
-            matter_id: None,
-        }),
-    )
-    .await
-    .expect("conflicts check should succeed");
-
-    assert!(resp.matched);
-    assert_eq!(resp.conflict.as_deref(), Some("Alpha Holdings"));
-    assert!(
-        resp.hits.is_empty(),
-        "legacy file fallback should not return db hits"
-    );
+    // Well past the intake cap, split between a CSV upload and a JSON list.
+    let mut csv = String::from("client_name,matter\n");
+    for idx in 0..150 {
+        csv.push_str(&format!("Prior Client {idx},M-{idx}\n"));
+    }
+    csv.push_str("acme corp,M-acme\n");
+    let request = |format: Option<&str>| BulkConflictCheckRequest {
+        names: vec!["Globex".to_string(), "Prior Client 3".to_string()],
+        csv: Some(csv.clone()),
+        label: Some("Lateral hire: J. Smith".to_string()),
+        format: format.map(str::to_string),
+    };
 
-    let events = crate::legal::audit::test_events_snapshot();
-    let event = events
+    let response =
+        legal_conflicts_bulk_check_handler(State(Arc::clone(&state)), Json(request(None)))
+            .await
+            .expect("bulk check");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body");
+    let report: serde_json::Value = serde_json::from_slice(&body).expect("json report");
+    assert_eq!(report["checked_count"], 152);
+    assert_eq!(report["matched_count"], 2);
+    let results = report["results"].as_array().expect("results");
+    let acme = results
         .iter()
-        .find(|entry| {
-            entry.event_type == "matter_conflict_check"
-                && entry.details.get("source").and_then(|v| v.as_str()) == Some("manual_text_check")
-                && entry.details.get("conflict").and_then(|v| v.as_str()) == Some("Alpha Holdings")
-        })
-        .expect("expected manual conflict check audit event");
-    assert_eq!(event.details["matched"], true);
-    assert_eq!(event.details["conflict"], "Alpha Holdings");
-    assert_eq!(event.details["source"], "manual_text_check");
+        .find(|row| row["name"] == "acme corp")
+        .expect("csv name screened");
+    assert_eq!(acme["matched"], true);
+    assert_eq!(acme["hits"][0]["matter_id"], "existing-matter");
+
+    let response =
+        legal_conflicts_bulk_check_handler(State(Arc::clone(&state)), Json(request(Some("csv"))))
+            .await
+            .expect("csv report");
     assert!(
-        event.details["text_preview"]
-            .as_str()
-            .is_some_and(|value| !value.is_empty())
+        response.headers()[axum::http::header::CONTENT_DISPOSITION]
+            .to_str()
+            .unwrap()
+            .starts_with("attachment;")
     );
-    assert!(events.iter().any(|entry| {
-        entry.event_type == "conflict_detected"
-            && entry.details.get("source").and_then(|v| v.as_str()) == Some("manual_text_check")
-    }));
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body");
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.starts_with("name,status,"));
+    assert!(body.contains("Globex,potential_conflict,Globex,adverse,existing-matter"));
+    assert!(body.contains("Prior Client 149,clear"));
+
+    let err = legal_conflicts_bulk_check_handler(
+        State(state),
+        Json(BulkConflictCheckRequest {
+            names: vec!["  ".to_string()],
+            csv: None,
+            label: None,
+            format: None,
+        }),
+    )
+    .await
+    .expect_err("empty list");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn conflicts_check_rejects_oversized_text_payload() {
+async fn watchlist_screens_new_matters_and_clients_and_alerts() {
+    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
+    crate::legal::audit::clear_test_events();
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    let mut legal = test_legal_config();
-    legal.enabled = true;
-    legal.require_matter_context = false;
-    legal.conflict_check_enabled = true;
-    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
 
-    let oversized = "A".repeat(MAX_CONFLICT_CHECK_TEXT_LEN + 1);
-    let err = matters_conflicts_check_handler(
-        State(state),
-        Json(MatterConflictCheckRequest {
-            text: oversized,
-            matter_id: None,
+    let err = watchlist_create_handler(
+        State(Arc::clone(&state)),
+        principal_with_role("staff-1", UserRole::Staff),
+        Json(CreateWatchlistEntryRequest {
+            name: "Foo LLC".to_string(),
+            category: None,
+            reason: None,
         }),
     )
     .await
-    .expect_err("oversized payload should be rejected");
+    .expect_err("staff cannot edit the watchlist");
+    assert_eq!(err.0, StatusCode::FORBIDDEN);
 
-    assert_eq!(err.0, StatusCode::BAD_REQUEST);
-    assert!(err.1.contains("at most"));
+    let (status, Json(entry)) = watchlist_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(CreateWatchlistEntryRequest {
+            name: "Foo LLC".to_string(),
+            category: Some("sanctioned".to_string()),
+            reason: Some("OFAC SDN list".to_string()),
+        }),
+    )
+    .await
+    .expect("add watchlist entry");
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(entry.category, "sanctioned");
+
+    let _ = matters_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(CreateMatterRequest {
+            matter_id: "Acme v. Foo".to_string(),
+            client: "Acme Corp".to_string(),
+            confidentiality: "attorney-client-privileged".to_string(),
+            retention: "follow-firm-policy".to_string(),
+            jurisdiction: None,
+            practice_area: None,
+            opened_date: None,
+            opened_at: None,
+            team: Vec::new(),
+            adversaries: vec!["foo, llc".to_string()],
+            conflict_decision: None,
+            conflict_note: None,
+            custom_fields: None,
+        }),
+    )
+    .await
+    .expect("watchlist matches never block matter creation");
+    let _ = clients_create_handler(
+        State(Arc::clone(&state)),
+        Json(CreateClientRequest {
+            name: "Unrelated Holdings".to_string(),
+            client_type: "entity".to_string(),
+            email: None,
+            phone: None,
+            address: None,
+            notes: None,
+        }),
+    )
+    .await
+    .expect("create client");
+
+    let events = crate::legal::audit::test_events_snapshot();
+    let matches: Vec<_> = events
+        .iter()
+        .filter(|event| event.event_type == "watchlist_match")
+        .collect();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].details["source"], "matter_created");
+    assert_eq!(matches[0].details["matches"][0]["matched_name"], "foo, llc");
+
+    let notifications = db
+        .list_notifications(
+            "test-user",
+            crate::db::NotificationFilter::All,
+            Some(crate::db::NotificationKind::System),
+            50,
+        )
+        .await
+        .expect("list notifications");
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].matter_id.as_deref(), Some("acme-v--foo"));
+    assert!(notifications[0].title.contains("Foo LLC"));
+
+    let Json(screen) = watchlist_screen_handler(
+        State(state),
+        Json(WatchlistScreenRequest {
+            names: vec!["Globex".to_string()],
+            text: Some("Payment routed through FOO LLC accounts.".to_string()),
+        }),
+    )
+    .await
+    .expect("screen");
+    assert!(screen.matched);
+    assert_eq!(screen.matches.len(), 1);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn conflicts_check_returns_db_hits_context() {
-    crate::legal::matter::reset_conflict_cache_for_tests();
+async fn intake_conflict_check_respects_disabled_policy() {
     let (db, _tmp) = crate::testing::test_db().await;
-    db.seed_matter_parties("existing-matter", "Acme Corp", &[], None)
-        .await
-        .expect("seed matter parties");
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     let mut legal = test_legal_config();
     legal.enabled = true;
-    legal.require_matter_context = false;
-    legal.conflict_check_enabled = true;
+    legal.conflict_check_enabled = false;
     let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
 
-    let Json(resp) = matters_conflicts_check_handler(
+    let err = matters_conflict_check_handler(
         State(state),
-        Json(MatterConflictCheckRequest {
-            text: "Please analyze exposure for Acme Corp".to_string(),
-            matter_id: None,
+        Json(MatterIntakeConflictCheckRequest {
+            matter_id: "new-matter".to_string(),
+            client_names: vec!["Acme Corp".to_string()],
+            adversary_names: vec![],
         }),
     )
     .await
-    .expect("conflicts check should succeed");
+    .expect_err("disabled policy should reject");
 
-    assert!(resp.matched);
-    assert_eq!(resp.conflict.as_deref(), Some("Acme Corp"));
-    assert!(!resp.hits.is_empty());
-    assert!(
-        resp.hits
-            .iter()
-            .any(|hit| hit.matter_id == "existing-matter")
-    );
+    assert_eq!(err.0, StatusCode::CONFLICT);
+    assert!(err.1.contains("disabled"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn conflicts_check_skips_file_fallback_when_db_authoritative_mode_enabled() {
-    crate::legal::matter::reset_conflict_cache_for_tests();
+async fn conflicts_reindex_backfills_graph_and_emits_audit_event() {
+    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
+    crate::legal::audit::clear_test_events();
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    workspace
+        .write(
+            "matters/demo/matter.yaml",
+            r#"
+matter_id: demo
+client: Demo Client
+team:
+  - Lead Counsel
+confidentiality: attorney-client-privileged
+adversaries:
+  - Foo Industries
+retention: follow-firm-policy
+opened_at: 2026-02-28
+"#,
+        )
+        .await
+        .expect("seed matter metadata");
     workspace
         .write(
             "conflicts.json",
-            r#"[{"name":"Fallback Party","aliases":["Fallback Co"]}]"#,
+            r#"[{"name":"Example Adverse Party","aliases":["Example Co"]}]"#,
         )
         .await
-        .expect("seed fallback conflicts");
+        .expect("seed conflicts");
 
     let mut legal = test_legal_config();
     legal.enabled = true;
-    legal.require_matter_context = false;
     legal.conflict_check_enabled = true;
-    legal.conflict_file_fallback_enabled = false;
-    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
+    let state = test_gateway_state_with_store_workspace_and_legal(
+        Arc::clone(&db),
+        Arc::clone(&workspace),
+        legal,
+    );
 
-    let Json(resp) = matters_conflicts_check_handler(
-        State(state),
-        Json(MatterConflictCheckRequest {
-            text: "Review communications with Fallback Party".to_string(),
-            matter_id: None,
-        }),
-    )
-    .await
-    .expect("manual conflict check should succeed");
+    let Json(resp) = matters_conflicts_reindex_handler(State(state))
+        .await
+        .expect("reindex should succeed");
 
-    assert!(!resp.matched);
-    assert!(resp.conflict.is_none());
-    assert!(resp.hits.is_empty());
+    assert_eq!(resp.status, "ok");
+    assert_eq!(resp.report.seeded_matters, 1);
+    assert_eq!(resp.report.global_conflicts_seeded, 1);
+    assert_eq!(resp.report.global_aliases_seeded, 1);
+
+    let alias_hits = db
+        .find_conflict_hits_for_names(&["Example Co".to_string()], 20)
+        .await
+        .expect("query seeded alias");
+    assert!(
+        alias_hits
+            .iter()
+            .any(|hit| { hit.matter_id == crate::legal::matter::GLOBAL_CONFLICT_GRAPH_MATTER_ID })
+    );
+
+    let events = crate::legal::audit::test_events_snapshot();
+    assert!(events.iter().any(|event| {
+        event.event_type == "conflict_graph_reindexed"
+            && event.details["seeded_matters"] == 1
+            && event.details["global_conflicts_seeded"] == 1
+    }));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn conflicts_check_rejects_empty_text() {
-    crate::legal::matter::reset_conflict_cache_for_tests();
+async fn conflicts_reindex_respects_disabled_policy() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    let state = test_gateway_state_with_store_and_workspace(db, workspace);
-
-    let err = matters_conflicts_check_handler(
-        State(state),
-        Json(MatterConflictCheckRequest {
-            text: "   ".to_string(),
-            matter_id: None,
-        }),
-    )
-    .await
-    .expect_err("empty text should fail");
+    let mut legal = test_legal_config();
+    legal.enabled = true;
+    legal.conflict_check_enabled = false;
+    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
 
-    assert_eq!(err.0, StatusCode::BAD_REQUEST);
-    assert!(err.1.contains("must not be empty"));
+    let err = matters_conflicts_reindex_handler(State(state))
+        .await
+        .expect_err("disabled policy should reject reindex");
+    assert_eq!(err.0, StatusCode::CONFLICT);
+    assert!(err.1.contains("disabled"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn conflicts_check_respects_disabled_config() {
-    crate::legal::matter::reset_conflict_cache_for_tests();
+async fn matters_create_requires_conflict_decision_when_hits_exist() {
     let (db, _tmp) = crate::testing::test_db().await;
+    db.seed_matter_parties("existing-matter", "Acme Corp", &[], None)
+        .await
+        .expect("seed matter parties");
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    let mut legal = test_legal_config();
-    legal.conflict_check_enabled = false;
-    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
+    let state = test_gateway_state_with_store_and_workspace(db, workspace);
 
-    let err = matters_conflicts_check_handler(
+    let err = matters_create_handler(
         State(state),
-        Json(MatterConflictCheckRequest {
-            text: "Alpha".to_string(),
-            matter_id: None,
+        owner_principal(),
+        Json(CreateMatterRequest {
+            matter_id: "new-matter".to_string(),
+            client: "Acme Corp".to_string(),
+            confidentiality: "privileged".to_string(),
+            retention: "policy".to_string(),
+            jurisdiction: None,
+            practice_area: None,
+            opened_date: None,
+            opened_at: None,
+            team: vec![],
+            adversaries: vec![],
+            conflict_decision: None,
+            conflict_note: None,
+            custom_fields: None,
         }),
     )
     .await
-    .expect_err("disabled conflict check should fail");
+    .expect_err("missing conflict decision should fail");
 
     assert_eq!(err.0, StatusCode::CONFLICT);
-    assert!(err.1.contains("disabled"));
+    let body: serde_json::Value =
+        serde_json::from_str(&err.1).expect("conflict body should be json");
+    assert_eq!(body["conflict_required"], true);
+    assert!(body["hits"].as_array().is_some_and(|hits| !hits.is_empty()));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn conflicts_check_requires_active_matter_when_policy_enabled() {
-    crate::legal::matter::reset_conflict_cache_for_tests();
+async fn matters_create_declined_records_audit_and_blocks_creation() {
+    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
+    crate::legal::audit::clear_test_events();
     let (db, _tmp) = crate::testing::test_db().await;
+    db.seed_matter_parties("existing-matter", "Acme Corp", &[], None)
+        .await
+        .expect("seed matter parties");
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    let mut legal = test_legal_config();
-    legal.enabled = true;
-    legal.conflict_check_enabled = true;
-    legal.require_matter_context = true;
-    legal.active_matter = None;
-    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
 
-    let err = matters_conflicts_check_handler(
+    let err = matters_create_handler(
         State(state),
-        Json(MatterConflictCheckRequest {
-            text: "Alpha".to_string(),
-            matter_id: None,
+        owner_principal(),
+        Json(CreateMatterRequest {
+            matter_id: "new-matter".to_string(),
+            client: "Acme Corp".to_string(),
+            confidentiality: "privileged".to_string(),
+            retention: "policy".to_string(),
+            jurisdiction: None,
+            practice_area: None,
+            opened_date: None,
+            opened_at: None,
+            team: vec![],
+            adversaries: vec![],
+            conflict_decision: Some(ConflictDecision::Declined),
+            conflict_note: Some("Escalated to conflicts counsel".to_string()),
+            custom_fields: None,
         }),
     )
     .await
-    .expect_err("missing matter context should fail");
+    .expect_err("declined decision should block creation");
 
-    assert_eq!(err.0, StatusCode::BAD_REQUEST);
-    assert!(err.1.contains("Active matter"));
+    assert_eq!(err.0, StatusCode::CONFLICT);
+    let body: serde_json::Value =
+        serde_json::from_str(&err.1).expect("declined body should be json");
+    assert_eq!(body["decision"], "declined");
+    let created = workspace.read("matters/new-matter/matter.yaml").await;
+    assert!(matches!(
+        created,
+        Err(crate::error::WorkspaceError::DocumentNotFound { .. })
+    ));
+
+    let events = crate::legal::audit::test_events_snapshot();
+    let decision_event = events
+        .iter()
+        .find(|event| event.event_type == "conflict_clearance_decision")
+        .expect("expected conflict_clearance_decision event");
+    assert_eq!(decision_event.details["decision"], "declined");
+    assert_eq!(decision_event.details["source"], "create_flow");
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn legal_audit_list_returns_empty_when_missing() {
+async fn matters_create_waived_records_and_proceeds() {
+    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
+    crate::legal::audit::clear_test_events();
     let (db, _tmp) = crate::testing::test_db().await;
+    db.seed_matter_parties("existing-matter", "Acme Corp", &[], None)
+        .await
+        .expect("seed matter parties");
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    let mut legal = test_legal_config();
-    legal.audit.enabled = true;
-    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
 
-    let Json(resp) = legal_audit_list_handler(State(state), Query(LegalAuditQuery::default()))
+    let (status, Json(resp)) = matters_create_handler(
+        State(state),
+        owner_principal(),
+        Json(CreateMatterRequest {
+            matter_id: "new-matter".to_string(),
+            client: "Acme Corp".to_string(),
+            confidentiality: "privileged".to_string(),
+            retention: "policy".to_string(),
+            jurisdiction: None,
+            practice_area: None,
+            opened_date: Some("2026-02-28".to_string()),
+            opened_at: None,
+            team: vec![],
+            adversaries: vec!["Other Party".to_string()],
+            conflict_decision: Some(ConflictDecision::Waived),
+            conflict_note: Some("Waived after documented informed consent".to_string()),
+            custom_fields: None,
+        }),
+    )
+    .await
+    .expect("waived decision should allow creation");
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(resp.matter.id, "new-matter");
+    workspace
+        .read("matters/new-matter/matter.yaml")
         .await
-        .expect("empty DB list should not error");
+        .expect("matter yaml should exist");
 
-    assert!(resp.events.is_empty());
-    assert_eq!(resp.total, 0);
-    assert_eq!(resp.next_offset, None);
+    let hits = db
+        .find_conflict_hits_for_names(&["Acme Corp".to_string()], 20)
+        .await
+        .expect("conflict search should succeed");
+    assert!(
+        hits.iter().any(|hit| hit.matter_id == "new-matter"),
+        "seed_matter_parties should register new matter parties"
+    );
+
+    let events = crate::legal::audit::test_events_snapshot();
+    assert!(events.iter().any(|event| {
+        event.event_type == "conflict_clearance_decision" && event.details["decision"] == "waived"
+    }));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn legal_audit_list_supports_filters_and_paging() {
+async fn matter_conflict_report_and_clearance_round_trip() {
     let (db, _tmp) = crate::testing::test_db().await;
-    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-
-    let mut legal = test_legal_config();
-    legal.audit.enabled = true;
+    db.seed_matter_parties("existing-matter", "Acme Corp", &[], None)
+        .await
+        .expect("seed existing matter parties");
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
     let state =
-        test_gateway_state_with_store_workspace_and_legal(Arc::clone(&db), workspace, legal);
-    let store = state.store.as_ref().expect("store should exist");
-    for idx in 1..=4 {
-        let severity = if idx % 2 == 0 {
-            crate::db::AuditSeverity::Warn
-        } else {
-            crate::db::AuditSeverity::Info
-        };
-        store
-            .append_audit_event(
-                &state.user_id,
-                &crate::db::AppendAuditEventParams {
-                    event_type: "approval_required".to_string(),
-                    actor: "gateway".to_string(),
-                    matter_id: Some("demo".to_string()),
-                    severity,
-                    details: serde_json::json!({ "id": idx }),
-                },
-            )
-            .await
-            .expect("append audit event");
-    }
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
 
-    let Json(resp) = legal_audit_list_handler(
+    let (_status, Json(party)) = matter_parties_upsert_handler(
         State(Arc::clone(&state)),
-        Query(LegalAuditQuery {
-            limit: Some(1),
-            offset: Some(0),
-            event_type: Some("approval_required".to_string()),
-            matter_id: Some("demo".to_string()),
-            severity: Some("warn".to_string()),
-            since: None,
-            until: None,
-            from: None,
-            to: None,
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(UpsertMatterPartyRequest {
+            name: "Acme Corp".to_string(),
+            role: "adverse".to_string(),
+            aliases: vec!["Acme Corporation".to_string()],
+            notes: Some("Named defendant".to_string()),
+            opened_at: None,
+            closed_at: None,
         }),
     )
     .await
-    .expect("audit list should succeed");
-
-    assert_eq!(resp.total, 2);
-    assert_eq!(resp.events.len(), 1);
-    assert_eq!(resp.next_offset, Some(1));
-    assert_eq!(resp.events[0].event_type, "approval_required");
-    assert_eq!(resp.events[0].matter_id.as_deref(), Some("demo"));
-    assert_eq!(resp.events[0].severity, "warn");
-}
-
-#[cfg(feature = "libsql")]
-#[tokio::test]
-async fn legal_audit_list_filters_since_until() {
-    let (db, _tmp) = crate::testing::test_db().await;
-    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    .expect("upsert adverse party");
+    assert_eq!(party.role, "adverse");
 
-    let mut legal = test_legal_config();
-    legal.audit.enabled = true;
-    let state =
-        test_gateway_state_with_store_workspace_and_legal(Arc::clone(&db), workspace, legal);
-    let store = state.store.as_ref().expect("store should exist");
-    store
-        .append_audit_event(
-            &state.user_id,
-            &crate::db::AppendAuditEventParams {
-                event_type: "matter_created".to_string(),
-                actor: "gateway".to_string(),
-                matter_id: Some("demo".to_string()),
-                severity: crate::db::AuditSeverity::Info,
-                details: serde_json::json!({ "step": 1 }),
-            },
-        )
-        .await
-        .expect("append");
-    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
-    let checkpoint = Utc::now().to_rfc3339();
-    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
-    store
-        .append_audit_event(
-            &state.user_id,
-            &crate::db::AppendAuditEventParams {
-                event_type: "matter_closed".to_string(),
-                actor: "gateway".to_string(),
-                matter_id: Some("demo".to_string()),
-                severity: crate::db::AuditSeverity::Critical,
-                details: serde_json::json!({ "step": 2 }),
-            },
-        )
-        .await
-        .expect("append");
+    let Json(report) = matter_conflicts_report_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("conflict report should succeed");
+    assert!(
+        report
+            .hits
+            .iter()
+            .any(|hit| hit.matter_id == "existing-matter")
+    );
+    assert!(report.latest_clearance.is_none());
 
-    let Json(resp) = legal_audit_list_handler(
-        State(state),
-        Query(LegalAuditQuery {
-            limit: Some(50),
-            offset: Some(0),
-            event_type: None,
-            matter_id: Some("demo".to_string()),
-            severity: None,
-            since: Some(checkpoint),
-            until: None,
-            from: None,
-            to: None,
+    let Json(clearance) = matter_conflicts_clearance_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(MatterConflictClearanceRequest {
+            decision: ConflictDecision::Waived,
+            note: Some("Waived after attorney review".to_string()),
+            reviewing_attorney: Some("Lead".to_string()),
         }),
     )
     .await
-    .expect("audit list should succeed");
+    .expect("clearance should be recorded");
+    assert_eq!(clearance.decision, "waived");
+    assert!(clearance.hit_count >= 1);
+    assert_eq!(
+        clearance.latest_clearance.reviewing_attorney.as_deref(),
+        Some("Lead")
+    );
 
-    assert_eq!(resp.total, 1);
-    assert_eq!(resp.events.len(), 1);
-    assert_eq!(resp.events[0].event_type, "matter_closed");
+    let Json(report_after) = matter_conflicts_report_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("report after clearance should succeed");
+    assert_eq!(
+        report_after
+            .latest_clearance
+            .as_ref()
+            .expect("latest clearance should exist")
+            .decision,
+        "waived"
+    );
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matters_documents_excludes_templates_by_default() {
+async fn matters_create_rejects_excessive_adversaries() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    workspace
-        .write("matters/demo/templates-archive/note.md", "archive note")
-        .await
-        .expect("seed templates-archive sibling");
     let state = test_gateway_state_with_store_and_workspace(db, workspace);
+    let adversaries: Vec<String> = (0..=MAX_INTAKE_CONFLICT_PARTIES)
+        .map(|idx| format!("Adverse Party {idx}"))
+        .collect();
 
-    let Json(resp) = matter_documents_handler(
+    let err = matters_create_handler(
         State(state),
         owner_principal(),
-        Path("demo".to_string()),
-        Query(MatterDocumentsQuery::default()),
+        Json(CreateMatterRequest {
+            matter_id: "new-matter".to_string(),
+            client: "Acme Corp".to_string(),
+            confidentiality: "privileged".to_string(),
+            retention: "policy".to_string(),
+            jurisdiction: None,
+            practice_area: None,
+            opened_date: None,
+            opened_at: None,
+            team: vec![],
+            adversaries,
+            conflict_decision: None,
+            conflict_note: None,
+            custom_fields: None,
+        }),
     )
     .await
-    .expect("documents request should succeed");
+    .expect_err("oversized adversary list should fail");
 
-    assert_eq!(resp.matter_id, "demo");
-    assert!(
-        !resp
-            .documents
-            .iter()
-            .any(|doc| doc.path.contains("/templates/"))
-    );
-    assert!(
-        resp.documents
-            .iter()
-            .any(|doc| doc.path == "matters/demo/notes.md")
-    );
-    assert!(
-        resp.documents
-            .iter()
-            .any(|doc| doc.path == "matters/demo/templates-archive/note.md")
-    );
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    assert!(err.1.contains("adversaries"));
+    assert!(err.1.contains("at most"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matters_documents_includes_templates_when_requested() {
+async fn conflicts_check_returns_hit_for_matching_entry() {
+    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
+    crate::legal::audit::clear_test_events();
+    crate::legal::matter::reset_conflict_cache_for_tests();
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state = test_gateway_state_with_store_and_workspace(db, workspace);
+    workspace
+        .write(
+            "conflicts.json",
+            r#"[{"name":"Alpha Holdings","aliases":["Alpha"]}]"#,
+        )
+        .await
+        .expect("seed conflicts");
 
-    let Json(resp) = matter_documents_handler(
+    let mut legal = test_legal_config();
+    legal.enabled = true;
+    legal.require_matter_context = false;
+    legal.conflict_check_enabled = true;
+    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
+
+    let Json(resp) = matters_conflicts_check_handler(
         State(state),
-        owner_principal(),
-        Path("demo".to_string()),
-        Query(MatterDocumentsQuery {
-            include_templates: Some(true),
+        Json(MatterConflictCheckRequest {
+            text: "Draft strategy for Alpha Holdings".to_string(),
+            matter_id: None,
         }),
     )
     .await
-    .expect("documents request should succeed");
+    .expect("conflicts check should succeed");
 
+    assert!(resp.matched);
+    assert_eq!(resp.conflict.as_deref(), Some("Alpha Holdings"));
     assert!(
-        resp.documents
-            .iter()
-            .any(|doc| doc.path == "matters/demo/templates/research_memo.md")
+        resp.hits.is_empty(),
+        "legacy file fallback should not return db hits"
     );
-}
 
-#[cfg(feature = "libsql")]
-#[tokio::test]
-async fn matters_templates_list_returns_expected_entries() {
-    let (db, _tmp) = crate::testing::test_db().await;
-    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state = test_gateway_state_with_store_and_workspace(db, workspace);
-
-    let Json(resp) =
-        matter_templates_handler(State(state), owner_principal(), Path("demo".to_string()))
-            .await
-            .expect("templates request should succeed");
-
-    assert_eq!(resp.matter_id, "demo");
-    assert_eq!(resp.templates.len(), 2);
-    assert_eq!(resp.templates[0].name, "chronology.md");
-    assert_eq!(resp.templates[1].name, "research_memo.md");
+    let events = crate::legal::audit::test_events_snapshot();
+    let event = events
+        .iter()
+        .find(|entry| {
+            entry.event_type == "matter_conflict_check"
+                && entry.details.get("source").and_then(|v| v.as_str()) == Some("manual_text_check")
+                && entry.details.get("conflict").and_then(|v| v.as_str()) == Some("Alpha Holdings")
+        })
+        .expect("expected manual conflict check audit event");
+    assert_eq!(event.details["matched"], true);
+    assert_eq!(event.details["conflict"], "Alpha Holdings");
+    assert_eq!(event.details["source"], "manual_text_check");
+    assert!(
+        event.details["text_preview"]
+            .as_str()
+            .is_some_and(|value| !value.is_empty())
+    );
+    assert!(events.iter().any(|entry| {
+        entry.event_type == "conflict_detected"
+            && entry.details.get("source").and_then(|v| v.as_str()) == Some("manual_text_check")
+    }));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matters_documents_backfill_incrementally_syncs_new_workspace_files() {
+async fn conflicts_check_rejects_oversized_text_payload() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-
-    let Json(initial) = matter_documents_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-        Query(MatterDocumentsQuery::default()),
-    )
-    .await
-    .expect("initial documents request should succeed");
-    assert!(
-        initial
-            .documents
-            .iter()
-            .any(|doc| doc.path == "matters/demo/notes.md")
-    );
-
-    workspace
-        .write(
-            "matters/demo/discovery/new-evidence.md",
-            "new evidence notes",
-        )
-        .await
-        .expect("seed new workspace document");
+    let mut legal = test_legal_config();
+    legal.enabled = true;
+    legal.require_matter_context = false;
+    legal.conflict_check_enabled = true;
+    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
 
-    let Json(updated) = matter_documents_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-        Query(MatterDocumentsQuery::default()),
+    let oversized = "A".repeat(MAX_CONFLICT_CHECK_TEXT_LEN + 1);
+    let err = matters_conflicts_check_handler(
+        State(state),
+        Json(MatterConflictCheckRequest {
+            text: oversized,
+            matter_id: None,
+        }),
     )
     .await
-    .expect("updated documents request should succeed");
-    assert!(
-        updated
-            .documents
-            .iter()
-            .any(|doc| doc.path == "matters/demo/discovery/new-evidence.md")
-    );
+    .expect_err("oversized payload should be rejected");
 
-    let linked = db
-        .list_matter_documents_db("test-user", "demo")
-        .await
-        .expect("matter documents query");
-    assert!(
-        linked
-            .iter()
-            .any(|doc| doc.path == "matters/demo/discovery/new-evidence.md")
-    );
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    assert!(err.1.contains("at most"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matters_templates_backfill_incrementally_syncs_new_workspace_templates() {
+async fn conflicts_check_returns_db_hits_context() {
+    crate::legal::matter::reset_conflict_cache_for_tests();
     let (db, _tmp) = crate::testing::test_db().await;
+    db.seed_matter_parties("existing-matter", "Acme Corp", &[], None)
+        .await
+        .expect("seed matter parties");
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    let mut legal = test_legal_config();
+    legal.enabled = true;
+    legal.require_matter_context = false;
+    legal.conflict_check_enabled = true;
+    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
 
-    let Json(initial) = matter_templates_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
+    let Json(resp) = matters_conflicts_check_handler(
+        State(state),
+        Json(MatterConflictCheckRequest {
+            text: "Please analyze exposure for Acme Corp".to_string(),
+            matter_id: None,
+        }),
     )
     .await
-    .expect("initial templates request should succeed");
-    assert_eq!(initial.templates.len(), 2);
-
-    workspace
-        .write(
-            "matters/demo/templates/witness_outline.md",
-            "# Witness Outline Template\n",
-        )
-        .await
-        .expect("seed new workspace template");
+    .expect("conflicts check should succeed");
 
-    let Json(updated) = matter_templates_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-    )
-    .await
-    .expect("updated templates request should succeed");
+    assert!(resp.matched);
+    assert_eq!(resp.conflict.as_deref(), Some("Acme Corp"));
+    assert!(!resp.hits.is_empty());
     assert!(
-        updated
-            .templates
+        resp.hits
             .iter()
-            .any(|template| template.name == "witness_outline.md")
+            .any(|hit| hit.matter_id == "existing-matter")
     );
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matters_documents_backfill_does_not_duplicate_initial_versions() {
+async fn conflicts_check_skips_file_fallback_when_db_authoritative_mode_enabled() {
+    crate::legal::matter::reset_conflict_cache_for_tests();
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-
-    let _ = matter_documents_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-        Query(MatterDocumentsQuery::default()),
-    )
-    .await
-    .expect("first documents request should succeed");
-
     workspace
         .write(
-            "matters/demo/discovery/new-evidence.md",
-            "new evidence notes",
+            "conflicts.json",
+            r#"[{"name":"Fallback Party","aliases":["Fallback Co"]}]"#,
         )
         .await
-        .expect("seed new workspace document");
+        .expect("seed fallback conflicts");
 
-    let _ = matter_documents_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-        Query(MatterDocumentsQuery::default()),
-    )
-    .await
-    .expect("second documents request should succeed");
+    let mut legal = test_legal_config();
+    legal.enabled = true;
+    legal.require_matter_context = false;
+    legal.conflict_check_enabled = true;
+    legal.conflict_file_fallback_enabled = false;
+    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
 
-    let _ = matter_documents_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-        Query(MatterDocumentsQuery::default()),
+    let Json(resp) = matters_conflicts_check_handler(
+        State(state),
+        Json(MatterConflictCheckRequest {
+            text: "Review communications with Fallback Party".to_string(),
+            matter_id: None,
+        }),
     )
     .await
-    .expect("third documents request should succeed");
+    .expect("manual conflict check should succeed");
 
-    let docs = db
-        .list_matter_documents_db("test-user", "demo")
-        .await
-        .expect("matter documents query");
-    for doc in docs {
-        let versions = db
-            .list_document_versions("test-user", doc.id)
-            .await
-            .expect("document versions query");
-        assert_eq!(
-            versions.len(),
-            1,
-            "document {} should have exactly one initial version",
-            doc.path
-        );
-    }
+    assert!(!resp.matched);
+    assert!(resp.conflict.is_none());
+    assert!(resp.hits.is_empty());
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matters_template_apply_creates_timestamped_draft() {
+async fn conflicts_check_rejects_empty_text() {
+    crate::legal::matter::reset_conflict_cache_for_tests();
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    let state = test_gateway_state_with_store_and_workspace(db, workspace);
 
-    let (status, Json(resp)) = matter_template_apply_handler(
+    let err = matters_conflicts_check_handler(
         State(state),
-        owner_principal(),
-        Path("demo".to_string()),
-        Json(MatterTemplateApplyRequest {
-            template_name: "chronology.md".to_string(),
+        Json(MatterConflictCheckRequest {
+            text: "   ".to_string(),
+            matter_id: None,
         }),
     )
     .await
-    .expect("apply template should succeed");
+    .expect_err("empty text should fail");
 
-    assert_eq!(status, StatusCode::CREATED);
-    let re = Regex::new(r"^matters/demo/drafts/chronology-\d{8}-\d{6}(-\d+)?\.md$")
-        .expect("valid regex");
-    assert!(
-        re.is_match(&resp.path),
-        "unexpected draft path: {}",
-        resp.path
-    );
-    let written = workspace
-        .read(&resp.path)
-        .await
-        .expect("draft should exist");
-    assert!(written.content.contains("# Chronology Template"));
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    assert!(err.1.contains("must not be empty"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matters_template_apply_avoids_overwrite_collisions() {
+async fn conflicts_check_respects_disabled_config() {
+    crate::legal::matter::reset_conflict_cache_for_tests();
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let matter_prefix = "matters/demo";
-    let fixed_ts = "20260226-120000";
-
-    let first = choose_template_apply_destination(
-        workspace.as_ref(),
-        matter_prefix,
-        "chronology.md",
-        fixed_ts,
-    )
-    .await
-    .expect("first destination");
-    workspace
-        .write(&first, "existing draft")
-        .await
-        .expect("seed collision");
+    let mut legal = test_legal_config();
+    legal.conflict_check_enabled = false;
+    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
 
-    let second = choose_template_apply_destination(
-        workspace.as_ref(),
-        matter_prefix,
-        "chronology.md",
-        fixed_ts,
+    let err = matters_conflicts_check_handler(
+        State(state),
+        Json(MatterConflictCheckRequest {
+            text: "Alpha".to_string(),
+            matter_id: None,
+        }),
     )
     .await
-    .expect("second destination");
+    .expect_err("disabled conflict check should fail");
 
-    assert_ne!(first, second);
-    assert!(
-        second.ends_with("-2.md"),
-        "expected -2 suffix, got {}",
-        second
-    );
+    assert_eq!(err.0, StatusCode::CONFLICT);
+    assert!(err.1.contains("disabled"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn documents_generate_creates_matter_link_and_version() {
+async fn conflicts_check_requires_active_matter_when_policy_enabled() {
+    crate::legal::matter::reset_conflict_cache_for_tests();
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-
-    // Ensure matter + client rows exist for docgen context.
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
-        .await
-        .expect("sync matter row");
-
-    let Json(templates_resp) = matter_templates_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-    )
-    .await
-    .expect("templates request should succeed");
-    let template_id = templates_resp
-        .templates
-        .iter()
-        .find(|template| template.name == "chronology.md")
-        .and_then(|template| template.id.clone())
-        .expect("template id should exist");
+    let mut legal = test_legal_config();
+    legal.enabled = true;
+    legal.conflict_check_enabled = true;
+    legal.require_matter_context = true;
+    legal.active_matter = None;
+    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
 
-    let (status, Json(resp)) = documents_generate_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Json(GenerateDocumentRequest {
-            template_id,
-            matter_id: "demo".to_string(),
-            extra: serde_json::json!({ "event": "hearing" }),
-            display_name: Some("Chronology Draft".to_string()),
-            category: Some("internal".to_string()),
-            label: Some("draft".to_string()),
+    let err = matters_conflicts_check_handler(
+        State(state),
+        Json(MatterConflictCheckRequest {
+            text: "Alpha".to_string(),
+            matter_id: None,
         }),
     )
     .await
-    .expect("generate request should succeed");
-
-    assert_eq!(status, StatusCode::CREATED);
-    assert!(resp.path.starts_with("matters/demo/drafts/chronology-"));
-
-    let generated = workspace
-        .read(&resp.path)
-        .await
-        .expect("generated doc exists");
-    assert!(
-        generated.content.contains("# Chronology Template"),
-        "rendered content should contain template body"
-    );
-
-    let matter_docs = db
-        .list_matter_documents_db("test-user", "demo")
-        .await
-        .expect("matter documents query");
-    let linked = matter_docs
-        .iter()
-        .find(|doc| doc.id.to_string() == resp.matter_document_id)
-        .expect("generated link should exist");
-    assert_eq!(linked.display_name, "Chronology Draft");
+    .expect_err("missing matter context should fail");
 
-    let versions = db
-        .list_document_versions("test-user", linked.id)
-        .await
-        .expect("document versions query");
-    assert_eq!(versions.len(), 1);
-    assert_eq!(versions[0].label, "draft");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    assert!(err.1.contains("Active matter"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matter_deadlines_handler_parses_calendar_rows() {
+async fn legal_audit_list_returns_empty_when_missing() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let today = Utc::now().date_naive();
-    let past = (today - chrono::TimeDelta::days(1)).to_string();
-    let upcoming = (today + chrono::TimeDelta::days(5)).to_string();
-    let followup = (today + chrono::TimeDelta::days(8)).to_string();
-
-    workspace
-            .write(
-                "matters/demo/deadlines/calendar.md",
-                &format!(
-                    "# Deadlines\n\n| Date | Deadline / Event | Owner | Status | Source |\n|---|---|---|---|---|\n| {past} | Initial disclosure due | Lead Counsel | open | FRCP 26 |\n| {upcoming} | File reply brief | Associate | drafting | court order |\n| {followup} | Submit witness list |  | open | scheduling order |\n"
-                ),
-            )
-            .await
-            .expect("seed deadlines calendar");
-
-    let state = test_gateway_state_with_store_and_workspace(db, workspace);
+    let mut legal = test_legal_config();
+    legal.audit.enabled = true;
+    let state = test_gateway_state_with_store_workspace_and_legal(db, workspace, legal);
 
-    let Json(resp) =
-        matter_deadlines_handler(State(state), owner_principal(), Path("demo".to_string()))
-            .await
-            .expect("deadlines handler should succeed");
+    let Json(resp) = legal_audit_list_handler(State(state), Query(LegalAuditQuery::default()))
+        .await
+        .expect("empty DB list should not error");
 
-    assert_eq!(resp.matter_id, "demo");
-    assert_eq!(resp.deadlines.len(), 3);
-    assert!(resp.deadlines[0].is_overdue);
-    assert!(!resp.deadlines[1].is_overdue);
-    assert_eq!(resp.deadlines[1].title, "File reply brief");
-    assert_eq!(resp.deadlines[2].title, "Submit witness list");
-    assert_eq!(resp.deadlines[2].owner, None);
-    assert_eq!(resp.deadlines[2].status.as_deref(), Some("open"));
-    assert_eq!(
-        resp.deadlines[2].source.as_deref(),
-        Some("scheduling order")
-    );
+    assert!(resp.events.is_empty());
+    assert_eq!(resp.total, 0);
+    assert_eq!(resp.next_offset, None);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matter_deadlines_db_entries_prefer_over_workspace_calendar() {
+async fn legal_audit_list_supports_filters_and_paging() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    workspace
-            .write(
-                "matters/demo/deadlines/calendar.md",
-                "| Date | Deadline / Event | Owner | Status | Source |\n|---|---|---|---|---|\n| 2030-01-01 | Legacy calendar row | Team | open | file |\n",
+
+    let mut legal = test_legal_config();
+    legal.audit.enabled = true;
+    let state =
+        test_gateway_state_with_store_workspace_and_legal(Arc::clone(&db), workspace, legal);
+    let store = state.store.as_ref().expect("store should exist");
+    for idx in 1..=4 {
+        let severity = if idx % 2 == 0 {
+            crate::db::AuditSeverity::Warn
+        } else {
+            crate::db::AuditSeverity::Info
+        };
+        store
+            .append_audit_event(
+                &state.user_id,
+                &crate::db::AppendAuditEventParams {
+                    event_type: "approval_required".to_string(),
+                    actor: "gateway".to_string(),
+                    matter_id: Some("demo".to_string()),
+                    severity,
+                    details: serde_json::json!({ "id": idx }),
+                },
             )
             .await
-            .expect("seed legacy calendar");
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+            .expect("append audit event");
+    }
 
-    let due_at = (Utc::now() + chrono::TimeDelta::days(7)).to_rfc3339();
-    let (status, Json(created)) = matter_deadlines_create_handler(
+    let Json(resp) = legal_audit_list_handler(
         State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-        Json(CreateMatterDeadlineRequest {
-            title: "File opposition brief".to_string(),
-            deadline_type: "filing".to_string(),
-            due_at: due_at.clone(),
-            completed_at: None,
-            reminder_days: vec![3],
-            rule_ref: Some("FRCP 56(c)(1)".to_string()),
-            computed_from: None,
-            task_id: None,
-            is_unsupported: None,
+        Query(LegalAuditQuery {
+            limit: Some(1),
+            offset: Some(0),
+            event_type: Some("approval_required".to_string()),
+            matter_id: Some("demo".to_string()),
+            severity: Some("warn".to_string()),
+            since: None,
+            until: None,
+            from: None,
+            to: None,
         }),
     )
     .await
-    .expect("create deadline should succeed");
-    assert_eq!(status, StatusCode::CREATED);
-    assert_eq!(created.title, "File opposition brief");
+    .expect("audit list should succeed");
 
-    let Json(resp) =
-        matter_deadlines_handler(State(state), owner_principal(), Path("demo".to_string()))
-            .await
-            .expect("deadlines handler should succeed");
-    assert_eq!(resp.deadlines.len(), 1);
-    assert_eq!(resp.deadlines[0].title, "File opposition brief");
-    assert_eq!(resp.deadlines[0].source.as_deref(), Some("FRCP 56(c)(1)"));
+    assert_eq!(resp.total, 2);
+    assert_eq!(resp.events.len(), 1);
+    assert_eq!(resp.next_offset, Some(1));
+    assert_eq!(resp.events[0].event_type, "approval_required");
+    assert_eq!(resp.events[0].matter_id.as_deref(), Some("demo"));
+    assert_eq!(resp.events[0].severity, "warn");
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn legal_court_rules_and_compute_deadline() {
+async fn legal_audit_list_filters_since_until() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state = test_gateway_state_with_store_and_workspace(db, workspace);
-
-    let Json(rules_resp) = legal_court_rules_handler()
-        .await
-        .expect("rules handler should succeed");
-    assert!(rules_resp.rules.iter().any(|rule| rule.id == "frcp_12_a_1"));
 
-    let Json(computed) = matter_deadlines_compute_handler(
-        State(state),
-        owner_principal(),
-        Path("demo".to_string()),
-        Json(MatterDeadlineComputeRequest {
-            rule_id: "frcp_12_a_1".to_string(),
-            trigger_date: "2026-03-02".to_string(),
-            title: Some("Response due".to_string()),
-            reminder_days: vec![7, 3],
-            computed_from: None,
-            task_id: None,
-            save: false,
+    let mut legal = test_legal_config();
+    legal.audit.enabled = true;
+    let state =
+        test_gateway_state_with_store_workspace_and_legal(Arc::clone(&db), workspace, legal);
+    let store = state.store.as_ref().expect("store should exist");
+    store
+        .append_audit_event(
+            &state.user_id,
+            &crate::db::AppendAuditEventParams {
+                event_type: "matter_created".to_string(),
+                actor: "gateway".to_string(),
+                matter_id: Some("demo".to_string()),
+                severity: crate::db::AuditSeverity::Info,
+                details: serde_json::json!({ "step": 1 }),
+            },
+        )
+        .await
+        .expect("append");
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    let checkpoint = Utc::now().to_rfc3339();
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    store
+        .append_audit_event(
+            &state.user_id,
+            &crate::db::AppendAuditEventParams {
+                event_type: "matter_closed".to_string(),
+                actor: "gateway".to_string(),
+                matter_id: Some("demo".to_string()),
+                severity: crate::db::AuditSeverity::Critical,
+                details: serde_json::json!({ "step": 2 }),
+            },
+        )
+        .await
+        .expect("append");
+
+    let Json(resp) = legal_audit_list_handler(
+        State(state),
+        Query(LegalAuditQuery {
+            limit: Some(50),
+            offset: Some(0),
+            event_type: None,
+            matter_id: Some("demo".to_string()),
+            severity: None,
+            since: Some(checkpoint),
+            until: None,
+            from: None,
+            to: None,
         }),
     )
     .await
-    .expect("compute handler should succeed");
-    assert_eq!(computed.rule.id, "frcp_12_a_1");
-    assert!(
-        computed.deadline.due_at.starts_with("2026-03-23T"),
-        "unexpected due_at {}",
-        computed.deadline.due_at
-    );
-    assert_eq!(computed.deadline.reminder_days, vec![3, 7]);
+    .expect("audit list should succeed");
+
+    assert_eq!(resp.total, 1);
+    assert_eq!(resp.events.len(), 1);
+    assert_eq!(resp.events[0].event_type, "matter_closed");
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matter_deadlines_patch_cascades_saved_compute_deadline_using_trace_rule_id() {
+async fn legal_costs_group_by_matter_and_report_caps() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
     let state =
         test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
-        .await
-        .expect("sync matter row");
-    let store = state.store.as_ref().expect("store should exist");
-
-    let rule = crate::legal::calendar::get_court_rule("frcp_12_a_1")
-        .expect("rules should parse")
-        .expect("rule should exist");
-    let mut trigger_due_at = Utc::now() + chrono::TimeDelta::days(90);
-    while crate::legal::calendar::apply_rule(&rule, trigger_due_at).date_naive()
-        == crate::legal::calendar::apply_rule(&rule, trigger_due_at + chrono::TimeDelta::days(1))
-            .date_naive()
-    {
-        trigger_due_at += chrono::TimeDelta::days(1);
-    }
-    let (_status, Json(trigger)) = matter_deadlines_create_handler(
+    let _ = matters_create_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path("demo".to_string()),
-        Json(CreateMatterDeadlineRequest {
-            title: "Service date".to_string(),
-            deadline_type: "court_date".to_string(),
-            due_at: trigger_due_at.to_rfc3339(),
-            completed_at: None,
-            reminder_days: vec![],
-            rule_ref: None,
-            computed_from: None,
-            task_id: None,
-            is_unsupported: None,
+        Json(CreateMatterRequest {
+            matter_id: "Acme v. Foo".to_string(),
+            client: "Acme Corp".to_string(),
+            confidentiality: "attorney-client-privileged".to_string(),
+            retention: "follow-firm-policy".to_string(),
+            jurisdiction: None,
+            practice_area: None,
+            opened_date: None,
+            opened_at: None,
+            team: Vec::new(),
+            adversaries: Vec::new(),
+            conflict_decision: None,
+            conflict_note: None,
+            custom_fields: None,
         }),
     )
     .await
-    .expect("create trigger deadline");
-    let trigger_id = trigger.id.clone();
+    .expect("create matter should succeed");
 
-    let Json(computed) = matter_deadlines_compute_handler(
+    let guard =
+        crate::agent::cost_guard::CostGuard::new(Default::default()).with_ledger(Arc::clone(&db));
+    let attribution =
+        crate::agent::cost_guard::CostAttribution::new("test-user", Some("acme-v--foo".into()));
+    guard
+        .record_llm_call("gpt-4o", 10_000, 10_000, None, Some(&attribution))
+        .await;
+    guard
+        .record_llm_call(
+            "gpt-4o",
+            1_000,
+            0,
+            None,
+            Some(&crate::agent::cost_guard::CostAttribution::new(
+                "test-user",
+                None,
+            )),
+        )
+        .await;
+
+    let Json(cap) = legal_cost_cap_put_handler(
         State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-        Json(MatterDeadlineComputeRequest {
-            rule_id: "frcp_12_a_1".to_string(),
-            trigger_date: trigger_due_at.to_rfc3339(),
-            title: Some("Response due".to_string()),
-            reminder_days: vec![5],
-            computed_from: Some(trigger_id.clone()),
-            task_id: None,
-            save: true,
+        Path("acme-v--foo".to_string()),
+        Json(MatterSpendCapRequest {
+            monthly_cap_cents: Some(10),
         }),
     )
     .await
-    .expect("compute+save should succeed");
-    let saved = computed.saved.expect("saved deadline should be returned");
-    assert_eq!(saved.rule_ref.as_deref(), Some("FRCP 12(a)(1)"));
+    .expect("set cap");
+    assert_eq!(cap.monthly_cap_cents, Some(10));
+    assert_eq!(cap.month_to_date_usd, "0.1250");
 
-    let dependent_id = Uuid::parse_str(&saved.id).expect("dependent uuid");
-    let before = store
-        .get_matter_deadline(&state.user_id, "demo", dependent_id)
-        .await
-        .expect("load dependent deadline before patch")
-        .expect("dependent deadline exists");
+    let Json(report) = legal_costs_handler(
+        State(Arc::clone(&state)),
+        Query(LegalCostsQuery {
+            group_by: Some("matter".to_string()),
+            ..Default::default()
+        }),
+    )
+    .await
+    .expect("cost report");
+    assert_eq!(report.group_by, "matter");
+    assert_eq!(report.total_usd, "0.1275");
+    assert_eq!(report.groups.len(), 2);
+    let acme = &report.groups[0];
+    assert_eq!(acme.key.as_deref(), Some("acme-v--foo"));
+    assert_eq!(acme.entries, 1);
+    assert_eq!(acme.monthly_cap_cents, Some(10));
+    assert_eq!(acme.cap_exceeded, Some(true));
+    assert_eq!(report.groups[1].key, None);
+    assert_eq!(report.groups[1].cap_exceeded, None);
 
-    let new_trigger_due_at = trigger_due_at + chrono::TimeDelta::days(1);
-    let expected_after_due_at = crate::legal::calendar::apply_rule(&rule, new_trigger_due_at);
-    let Json(_updated_trigger) = matter_deadlines_patch_handler(
+    let Json(by_client) = legal_costs_handler(
         State(Arc::clone(&state)),
-        owner_principal(),
-        Path(("demo".to_string(), trigger_id)),
-        Json(UpdateMatterDeadlineRequest {
-            title: None,
-            deadline_type: None,
-            due_at: Some(new_trigger_due_at.to_rfc3339()),
-            completed_at: None,
-            reminder_days: None,
-            rule_ref: None,
-            computed_from: None,
-            task_id: None,
-            is_unsupported: None,
+        Query(LegalCostsQuery {
+            group_by: Some("client".to_string()),
+            ..Default::default()
         }),
     )
     .await
-    .expect("patch should succeed");
+    .expect("client report");
+    assert!(by_client.groups.iter().any(|g| g.key.is_some()));
 
-    let after = store
-        .get_matter_deadline(&state.user_id, "demo", dependent_id)
-        .await
-        .expect("load dependent deadline after patch")
-        .expect("dependent deadline exists");
-    assert_ne!(before.due_at, expected_after_due_at);
-    assert_eq!(after.due_at, expected_after_due_at);
-    assert_eq!(
-        after
-            .explanation
-            .as_ref()
-            .and_then(|value| value.get("rule_id"))
-            .and_then(|value| value.as_str()),
-        Some("frcp_12_a_1")
-    );
+    let err = legal_costs_handler(
+        State(Arc::clone(&state)),
+        Query(LegalCostsQuery {
+            group_by: Some("week".to_string()),
+            ..Default::default()
+        }),
+    )
+    .await
+    .expect_err("unknown grouping");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+
+    let err = legal_cost_cap_put_handler(
+        State(state),
+        Path("missing".to_string()),
+        Json(MatterSpendCapRequest {
+            monthly_cap_cents: Some(10),
+        }),
+    )
+    .await
+    .expect_err("unknown matter");
+    assert_eq!(err.0, StatusCode::NOT_FOUND);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matter_deadline_override_cascades_dependents_and_resyncs_reminders() {
+async fn matters_documents_excludes_templates_by_default() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+    workspace
+        .write("matters/demo/templates-archive/note.md", "archive note")
         .await
-        .expect("sync matter row");
-    let store = state.store.as_ref().expect("store should exist");
+        .expect("seed templates-archive sibling");
+    let state = test_gateway_state_with_store_and_workspace(db, workspace);
 
-    let rule = crate::legal::calendar::get_court_rule("frcp_12_a_1")
-        .expect("rules should parse")
-        .expect("rule should exist");
-    let mut trigger_due_at = Utc::now() + chrono::TimeDelta::days(120);
-    while crate::legal::calendar::apply_rule(&rule, trigger_due_at).date_naive()
-        == crate::legal::calendar::apply_rule(&rule, trigger_due_at + chrono::TimeDelta::days(1))
-            .date_naive()
-    {
-        trigger_due_at += chrono::TimeDelta::days(1);
-    }
-    let (_status, Json(trigger)) = matter_deadlines_create_handler(
-        State(Arc::clone(&state)),
+    let Json(resp) = matter_documents_handler(
+        State(state),
         owner_principal(),
         Path("demo".to_string()),
-        Json(CreateMatterDeadlineRequest {
-            title: "Service date".to_string(),
-            deadline_type: "court_date".to_string(),
-            due_at: trigger_due_at.to_rfc3339(),
-            completed_at: None,
-            reminder_days: vec![],
-            rule_ref: None,
-            computed_from: None,
-            task_id: None,
-            is_unsupported: None,
-        }),
+        Query(MatterDocumentsQuery::default()),
     )
     .await
-    .expect("create trigger deadline");
-    let trigger_id = trigger.id.clone();
+    .expect("documents request should succeed");
 
-    let Json(computed) = matter_deadlines_compute_handler(
-        State(Arc::clone(&state)),
+    assert_eq!(resp.matter_id, "demo");
+    assert!(
+        !resp
+            .documents
+            .iter()
+            .any(|doc| doc.path.contains("/templates/"))
+    );
+    assert!(
+        resp.documents
+            .iter()
+            .any(|doc| doc.path == "matters/demo/notes.md")
+    );
+    assert!(
+        resp.documents
+            .iter()
+            .any(|doc| doc.path == "matters/demo/templates-archive/note.md")
+    );
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matters_documents_includes_templates_when_requested() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state = test_gateway_state_with_store_and_workspace(db, workspace);
+
+    let Json(resp) = matter_documents_handler(
+        State(state),
         owner_principal(),
         Path("demo".to_string()),
-        Json(MatterDeadlineComputeRequest {
-            rule_id: "frcp_12_a_1".to_string(),
-            trigger_date: trigger_due_at.to_rfc3339(),
-            title: Some("Response due".to_string()),
-            reminder_days: vec![7],
-            computed_from: Some(trigger_id.clone()),
-            task_id: None,
-            save: true,
+        Query(MatterDocumentsQuery {
+            include_templates: Some(true),
         }),
     )
     .await
-    .expect("compute+save should succeed");
-
-    let dependent = computed.saved.expect("saved deadline should be returned");
-    let dependent_id = Uuid::parse_str(&dependent.id).expect("dependent uuid");
-    let dependent_before = store
-        .get_matter_deadline(&state.user_id, "demo", dependent_id)
-        .await
-        .expect("load dependent deadline before override")
-        .expect("dependent deadline exists");
+    .expect("documents request should succeed");
 
-    let prefix = deadline_reminder_prefix("demo", dependent_id);
-    let routines_before = db.list_routines("test-user").await.expect("list routines");
-    let routine_before = routines_before
-        .into_iter()
-        .find(|routine| routine.name.starts_with(&prefix) && routine.enabled)
-        .expect("dependent reminder routine should exist");
-    let next_fire_before = routine_before
-        .next_fire_at
-        .expect("dependent reminder should have next fire");
+    assert!(
+        resp.documents
+            .iter()
+            .any(|doc| doc.path == "matters/demo/templates/research_memo.md")
+    );
+}
 
-    let new_trigger_due_at = trigger_due_at + chrono::TimeDelta::days(1);
-    let expected_after_due_at = crate::legal::calendar::apply_rule(&rule, new_trigger_due_at);
-    let Json(_updated_trigger) = matter_deadline_override_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path(("demo".to_string(), trigger_id)),
-        Json(DeadlineOverrideRequest {
-            due_at: new_trigger_due_at.to_rfc3339(),
-            reason: "Stipulated extension".to_string(),
-        }),
-    )
-    .await
-    .expect("override should succeed");
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matters_templates_list_returns_expected_entries() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state = test_gateway_state_with_store_and_workspace(db, workspace);
 
-    let dependent_after = store
-        .get_matter_deadline(&state.user_id, "demo", dependent_id)
-        .await
-        .expect("load dependent deadline after override")
-        .expect("dependent deadline exists");
-    assert_ne!(dependent_before.due_at, expected_after_due_at);
-    assert_eq!(dependent_after.due_at, expected_after_due_at);
+    let Json(resp) =
+        matter_templates_handler(State(state), owner_principal(), Path("demo".to_string()))
+            .await
+            .expect("templates request should succeed");
 
-    let routines_after = db
-        .list_routines("test-user")
-        .await
-        .expect("list routines after override");
-    let routine_after = routines_after
-        .into_iter()
-        .find(|routine| routine.name.starts_with(&prefix) && routine.enabled)
-        .expect("dependent reminder routine should still exist");
-    let next_fire_after = routine_after
-        .next_fire_at
-        .expect("dependent reminder should have next fire after override");
-    assert!(
-        next_fire_after > next_fire_before,
-        "expected rescheduled reminder after override"
-    );
+    assert_eq!(resp.matter_id, "demo");
+    assert_eq!(resp.templates.len(), 2);
+    assert_eq!(resp.templates[0].name, "chronology.md");
+    assert_eq!(resp.templates[1].name, "research_memo.md");
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matter_deadline_delete_disables_reminder_routines() {
+async fn matters_documents_backfill_incrementally_syncs_new_workspace_files() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
     let state =
         test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
 
-    let due_at = (Utc::now() + chrono::TimeDelta::days(10)).to_rfc3339();
-    let (_status, Json(created)) = matter_deadlines_create_handler(
+    let Json(initial) = matter_documents_handler(
         State(Arc::clone(&state)),
         owner_principal(),
         Path("demo".to_string()),
-        Json(CreateMatterDeadlineRequest {
-            title: "Serve discovery requests".to_string(),
-            deadline_type: "discovery_cutoff".to_string(),
-            due_at,
-            completed_at: None,
-            reminder_days: vec![1, 3],
-            rule_ref: None,
-            computed_from: None,
-            task_id: None,
-            is_unsupported: None,
-        }),
+        Query(MatterDocumentsQuery::default()),
     )
     .await
-    .expect("create deadline should succeed");
-
-    let deadline_id = Uuid::parse_str(&created.id).expect("deadline uuid");
-    let prefix = deadline_reminder_prefix("demo", deadline_id);
+    .expect("initial documents request should succeed");
+    assert!(
+        initial
+            .documents
+            .iter()
+            .any(|doc| doc.path == "matters/demo/notes.md")
+    );
 
-    let before_delete = db
-        .list_routines("test-user")
+    workspace
+        .write(
+            "matters/demo/discovery/new-evidence.md",
+            "new evidence notes",
+        )
         .await
-        .expect("list routines before delete");
-    let active_count = before_delete
-        .iter()
-        .filter(|routine| routine.name.starts_with(&prefix) && routine.enabled)
-        .count();
-    assert_eq!(active_count, 2);
+        .expect("seed new workspace document");
 
-    let status = matter_deadlines_delete_handler(
-        State(state),
+    let Json(updated) = matter_documents_handler(
+        State(Arc::clone(&state)),
         owner_principal(),
-        Path(("demo".to_string(), created.id.clone())),
+        Path("demo".to_string()),
+        Query(MatterDocumentsQuery::default()),
     )
     .await
-    .expect("delete deadline should succeed");
-    assert_eq!(status, StatusCode::NO_CONTENT);
+    .expect("updated documents request should succeed");
+    assert!(
+        updated
+            .documents
+            .iter()
+            .any(|doc| doc.path == "matters/demo/discovery/new-evidence.md")
+    );
 
-    let after_delete = db
-        .list_routines("test-user")
+    let linked = db
+        .list_matter_documents_db("test-user", "demo")
         .await
-        .expect("list routines after delete");
-    let routines: Vec<_> = after_delete
-        .into_iter()
-        .filter(|routine| routine.name.starts_with(&prefix))
-        .collect();
-    assert_eq!(routines.len(), 2);
-    assert!(routines.iter().all(|routine| !routine.enabled));
+        .expect("matter documents query");
+    assert!(
+        linked
+            .iter()
+            .any(|doc| doc.path == "matters/demo/discovery/new-evidence.md")
+    );
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matter_dashboard_reports_workflow_scorecard() {
+async fn matters_templates_backfill_incrementally_syncs_new_workspace_templates() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
-    let today = Utc::now().date_naive();
-    let upcoming = (today + chrono::TimeDelta::days(7)).to_string();
-    let overdue = (today - chrono::TimeDelta::days(2)).to_string();
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+
+    let Json(initial) = matter_templates_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("initial templates request should succeed");
+    assert_eq!(initial.templates.len(), 2);
 
-    workspace
-        .write("matters/demo/drafts/first-brief.md", "Draft body")
-        .await
-        .expect("seed draft");
-    workspace
-        .write(
-            "matters/demo/workflows/intake_checklist.md",
-            "- [x] Engagement confirmed\n- [ ] Conflict memo attached\n",
-        )
-        .await
-        .expect("seed intake checklist");
     workspace
         .write(
-            "matters/demo/workflows/review_and_filing_checklist.md",
-            "- [x] Citation format pass complete\n- [ ] Partner sign-off recorded\n",
+            "matters/demo/templates/witness_outline.md",
+            "# Witness Outline Template\n",
         )
         .await
-        .expect("seed review checklist");
-    workspace
-            .write(
-                "matters/demo/deadlines/calendar.md",
-                &format!(
-                    "| Date | Deadline / Event | Owner | Status | Source |\n|---|---|---|---|---|\n| {overdue} | Serve disclosures | Team | open | docket |\n| {upcoming} | File opposition | Team | open | order |\n"
-                ),
-            )
-            .await
-            .expect("seed deadlines");
-
-    let state = test_gateway_state_with_store_and_workspace(db, workspace);
-    let Json(resp) =
-        matter_dashboard_handler(State(state), owner_principal(), Path("demo".to_string()))
-            .await
-            .expect("dashboard handler should succeed");
+        .expect("seed new workspace template");
 
-    assert_eq!(resp.matter_id, "demo");
-    assert_eq!(resp.template_count, 2);
-    assert_eq!(resp.draft_count, 1);
-    assert_eq!(resp.checklist_completed, 2);
-    assert_eq!(resp.checklist_total, 4);
-    assert_eq!(resp.overdue_deadlines, 1);
-    assert_eq!(resp.upcoming_deadlines_14d, 1);
-    assert_eq!(
-        resp.next_deadline.as_ref().map(|item| item.date.as_str()),
-        Some(upcoming.as_str())
+    let Json(updated) = matter_templates_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("updated templates request should succeed");
+    assert!(
+        updated
+            .templates
+            .iter()
+            .any(|template| template.name == "witness_outline.md")
     );
-    assert!(resp.document_count >= 6);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matter_filing_package_creates_export_index() {
+async fn matters_documents_backfill_does_not_duplicate_initial_versions() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+
+    let _ = matter_documents_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Query(MatterDocumentsQuery::default()),
+    )
+    .await
+    .expect("first documents request should succeed");
+
     workspace
         .write(
-            "matters/demo/workflows/intake_checklist.md",
-            "- [x] Intake complete\n",
+            "matters/demo/discovery/new-evidence.md",
+            "new evidence notes",
         )
         .await
-        .expect("seed checklist");
-    workspace
-            .write(
-                "matters/demo/deadlines/calendar.md",
-                "| Date | Deadline / Event | Owner | Status | Source |\n|---|---|---|---|---|\n| 2027-01-15 | File status report | Team | open | order |\n",
-            )
-            .await
-            .expect("seed deadlines");
-
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-
-    let (status, Json(resp)) =
-        matter_filing_package_handler(State(state), owner_principal(), Path("demo".to_string()))
-            .await
-            .expect("filing package should be generated");
+        .expect("seed new workspace document");
+
+    let _ = matter_documents_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Query(MatterDocumentsQuery::default()),
+    )
+    .await
+    .expect("second documents request should succeed");
+
+    let _ = matter_documents_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Query(MatterDocumentsQuery::default()),
+    )
+    .await
+    .expect("third documents request should succeed");
+
+    let docs = db
+        .list_matter_documents_db("test-user", "demo")
+        .await
+        .expect("matter documents query");
+    for doc in docs {
+        let versions = db
+            .list_document_versions("test-user", doc.id)
+            .await
+            .expect("document versions query");
+        assert_eq!(
+            versions.len(),
+            1,
+            "document {} should have exactly one initial version",
+            doc.path
+        );
+    }
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matters_template_apply_creates_timestamped_draft() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+
+    let (status, Json(resp)) = matter_template_apply_handler(
+        State(state),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(MatterTemplateApplyRequest {
+            template_name: "chronology.md".to_string(),
+        }),
+    )
+    .await
+    .expect("apply template should succeed");
 
     assert_eq!(status, StatusCode::CREATED);
-    assert_eq!(resp.matter_id, "demo");
+    let re = Regex::new(r"^matters/demo/drafts/chronology-\d{8}-\d{6}(-\d+)?\.md$")
+        .expect("valid regex");
     assert!(
+        re.is_match(&resp.path),
+        "unexpected draft path: {}",
         resp.path
-            .starts_with("matters/demo/exports/filing-package-")
     );
-
-    let exported = workspace
+    let written = workspace
         .read(&resp.path)
         .await
-        .expect("filing package file should exist");
-    assert!(exported.content.contains("# Filing Package Index"));
-    assert!(exported.content.contains("matters/demo/notes.md"));
-    assert!(exported.content.contains("Template Inventory"));
+        .expect("draft should exist");
+    assert!(written.content.contains("# Chronology Template"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matter_citation_workflow_requires_ready_filing_document_before_export() {
+async fn matters_template_apply_avoids_overwrite_collisions() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let matter_prefix = "matters/demo";
+    let fixed_ts = "20260226-120000";
+
+    let first = choose_template_apply_destination(
+        workspace.as_ref(),
+        matter_prefix,
+        "chronology.md",
+        fixed_ts,
+    )
+    .await
+    .expect("first destination");
+    workspace
+        .write(&first, "existing draft")
+        .await
+        .expect("seed collision");
+
+    let second = choose_template_apply_destination(
+        workspace.as_ref(),
+        matter_prefix,
+        "chronology.md",
+        fixed_ts,
+    )
+    .await
+    .expect("second destination");
+
+    assert_ne!(first, second);
+    assert!(
+        second.ends_with("-2.md"),
+        "expected -2 suffix, got {}",
+        second
+    );
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn documents_generate_creates_matter_link_and_version() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
     let state =
         test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+
+    // Ensure matter + client rows exist for docgen context.
     ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
         .await
         .expect("sync matter row");
-    let store = state.store.as_ref().expect("store should exist");
 
-    let written = workspace
-        .write(
-            "matters/demo/filings/brief.md",
-            "Draft brief cites Roe v. Wade, 410 U.S. 113 (1973).\n",
-        )
-        .await
-        .expect("seed filing document");
-    let matter_document = store
-        .upsert_matter_document(
-            &state.user_id,
-            "demo",
-            &crate::db::UpsertMatterDocumentParams {
-                memory_document_id: written.id,
-                path: written.path.clone(),
-                display_name: "Draft Brief".to_string(),
-                category: crate::db::MatterDocumentCategory::Filing,
-                readiness_state: Some(crate::db::DocumentReadinessState::Draft),
-            },
-        )
-        .await
-        .expect("link filing document");
-    crate::channels::web::server::backfill_matter_documents_from_workspace(state.as_ref(), "demo")
+    let Json(templates_resp) = matter_templates_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("templates request should succeed");
+    let template_id = templates_resp
+        .templates
+        .iter()
+        .find(|template| template.name == "chronology.md")
+        .and_then(|template| template.id.clone())
+        .expect("template id should exist");
+
+    state.spawn_event_subscribers();
+    let mut sse = Box::pin(state.sse.subscribe_raw().expect("sse subscription"));
+
+    let (status, Json(resp)) = documents_generate_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(GenerateDocumentRequest {
+            template_id,
+            matter_id: "demo".to_string(),
+            extra: serde_json::json!({ "event": "hearing" }),
+            display_name: Some("Chronology Draft".to_string()),
+            category: Some("internal".to_string()),
+            label: Some("draft".to_string()),
+            locale: None,
+        }),
+    )
+    .await
+    .expect("generate request should succeed");
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(resp.path.starts_with("matters/demo/drafts/chronology-"));
+
+    let generated = workspace
+        .read(&resp.path)
         .await
-        .expect("backfill matter documents");
-    let docs = store
-        .list_matter_documents_db(&state.user_id, "demo")
+        .expect("generated doc exists");
+    assert!(
+        generated.content.contains("# Chronology Template"),
+        "rendered content should contain template body"
+    );
+
+    let matter_docs = db
+        .list_matter_documents_db("test-user", "demo")
         .await
-        .expect("list matter documents");
-    let blocking_doc = docs
+        .expect("matter documents query");
+    let linked = matter_docs
         .iter()
-        .find(|doc| doc.id == matter_document.id)
-        .expect("filing document should be present in db");
-    assert_eq!(
-        blocking_doc.category,
-        crate::db::MatterDocumentCategory::Filing
-    );
+        .find(|doc| doc.id.to_string() == resp.matter_document_id)
+        .expect("generated link should exist");
+    assert_eq!(linked.display_name, "Chronology Draft");
+
+    let versions = db
+        .list_document_versions("test-user", linked.id)
+        .await
+        .expect("document versions query");
+    assert_eq!(versions.len(), 1);
+    assert_eq!(versions[0].label, "draft");
+
+    // The document.generated event reaches the SSE stream and the audit log.
+    use futures::StreamExt;
+    let event = tokio::time::timeout(std::time::Duration::from_secs(2), sse.next())
+        .await
+        .expect("domain event should be forwarded")
+        .expect("sse stream open");
+    match event {
+        SseEvent::Domain {
+            event,
+            matter_id,
+            data,
+        } => {
+            assert_eq!(event, "document.generated");
+            assert_eq!(matter_id.as_deref(), Some("demo"));
+            assert_eq!(data["document_id"], resp.matter_document_id);
+        }
+        other => panic!("unexpected SSE event: {other:?}"),
+    }
+    let query = crate::db::AuditEventQuery {
+        event_type: Some("document_generated".to_string()),
+        ..Default::default()
+    };
+    let mut audited = Vec::new();
+    for _ in 0..100 {
+        audited = db
+            .list_audit_events("test-user", &query, 10, 0)
+            .await
+            .expect("audit query");
+        if !audited.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert_eq!(audited.len(), 1);
+    assert_eq!(audited[0].matter_id.as_deref(), Some("demo"));
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_deadlines_handler_parses_calendar_rows() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let today = Utc::now().date_naive();
+    let past = (today - chrono::TimeDelta::days(1)).to_string();
+    let upcoming = (today + chrono::TimeDelta::days(5)).to_string();
+    let followup = (today + chrono::TimeDelta::days(8)).to_string();
+
+    workspace
+            .write(
+                "matters/demo/deadlines/calendar.md",
+                &format!(
+                    "# Deadlines\n\n| Date | Deadline / Event | Owner | Status | Source |\n|---|---|---|---|---|\n| {past} | Initial disclosure due | Lead Counsel | open | FRCP 26 |\n| {upcoming} | File reply brief | Associate | drafting | court order |\n| {followup} | Submit witness list |  | open | scheduling order |\n"
+                ),
+            )
+            .await
+            .expect("seed deadlines calendar");
+
+    let state = test_gateway_state_with_store_and_workspace(db, workspace);
+
+    let Json(resp) =
+        matter_deadlines_handler(State(state), owner_principal(), Path("demo".to_string()))
+            .await
+            .expect("deadlines handler should succeed");
+
+    assert_eq!(resp.matter_id, "demo");
+    assert_eq!(resp.deadlines.len(), 3);
+    assert!(resp.deadlines[0].is_overdue);
+    assert!(!resp.deadlines[1].is_overdue);
+    assert_eq!(resp.deadlines[1].title, "File reply brief");
+    assert_eq!(resp.deadlines[2].title, "Submit witness list");
+    assert_eq!(resp.deadlines[2].owner, None);
+    assert_eq!(resp.deadlines[2].status.as_deref(), Some("open"));
     assert_eq!(
-        blocking_doc.readiness_state,
-        crate::db::DocumentReadinessState::Draft
+        resp.deadlines[2].source.as_deref(),
+        Some("scheduling order")
     );
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_deadlines_db_entries_prefer_over_workspace_calendar() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    workspace
+            .write(
+                "matters/demo/deadlines/calendar.md",
+                "| Date | Deadline / Event | Owner | Status | Source |\n|---|---|---|---|---|\n| 2030-01-01 | Legacy calendar row | Team | open | file |\n",
+            )
+            .await
+            .expect("seed legacy calendar");
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+
+    let due_at = (Utc::now() + chrono::TimeDelta::days(7)).to_rfc3339();
+    let (status, Json(created)) = matter_deadlines_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateMatterDeadlineRequest {
+            title: "File opposition brief".to_string(),
+            deadline_type: "filing".to_string(),
+            due_at: due_at.clone(),
+            completed_at: None,
+            reminder_days: vec![3],
+            rule_ref: Some("FRCP 56(c)(1)".to_string()),
+            computed_from: None,
+            task_id: None,
+            is_unsupported: None,
+        }),
+    )
+    .await
+    .expect("create deadline should succeed");
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created.title, "File opposition brief");
+
+    let Json(resp) =
+        matter_deadlines_handler(State(state), owner_principal(), Path("demo".to_string()))
+            .await
+            .expect("deadlines handler should succeed");
+    assert_eq!(resp.deadlines.len(), 1);
+    assert_eq!(resp.deadlines[0].title, "File opposition brief");
+    assert_eq!(resp.deadlines[0].source.as_deref(), Some("FRCP 56(c)(1)"));
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn legal_court_rules_and_compute_deadline() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state = test_gateway_state_with_store_and_workspace(db, workspace);
+
+    let Json(rules_resp) = legal_court_rules_handler()
+        .await
+        .expect("rules handler should succeed");
+    assert!(rules_resp.rules.iter().any(|rule| rule.id == "frcp_12_a_1"));
+
+    let Json(computed) = matter_deadlines_compute_handler(
+        State(state),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(MatterDeadlineComputeRequest {
+            rule_id: "frcp_12_a_1".to_string(),
+            trigger_date: "2026-03-02".to_string(),
+            title: Some("Response due".to_string()),
+            reminder_days: vec![7, 3],
+            computed_from: None,
+            task_id: None,
+            save: false,
+        }),
+    )
+    .await
+    .expect("compute handler should succeed");
+    assert_eq!(computed.rule.id, "frcp_12_a_1");
+    assert!(
+        computed.deadline.due_at.starts_with("2026-03-23T"),
+        "unexpected due_at {}",
+        computed.deadline.due_at
+    );
+    assert_eq!(computed.deadline.reminder_days, vec![3, 7]);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_deadlines_patch_cascades_saved_compute_deadline_using_trace_rule_id() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
+
+    let rule = crate::legal::calendar::get_court_rule("frcp_12_a_1")
+        .expect("rules should parse")
+        .expect("rule should exist");
+    let mut trigger_due_at = Utc::now() + chrono::TimeDelta::days(90);
+    while crate::legal::calendar::apply_rule(&rule, trigger_due_at).date_naive()
+        == crate::legal::calendar::apply_rule(&rule, trigger_due_at + chrono::TimeDelta::days(1))
+            .date_naive()
+    {
+        trigger_due_at += chrono::TimeDelta::days(1);
+    }
+    let (_status, Json(trigger)) = matter_deadlines_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateMatterDeadlineRequest {
+            title: "Service date".to_string(),
+            deadline_type: "court_date".to_string(),
+            due_at: trigger_due_at.to_rfc3339(),
+            completed_at: None,
+            reminder_days: vec![],
+            rule_ref: None,
+            computed_from: None,
+            task_id: None,
+            is_unsupported: None,
+        }),
+    )
+    .await
+    .expect("create trigger deadline");
+    let trigger_id = trigger.id.clone();
+
+    let Json(computed) = matter_deadlines_compute_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(MatterDeadlineComputeRequest {
+            rule_id: "frcp_12_a_1".to_string(),
+            trigger_date: trigger_due_at.to_rfc3339(),
+            title: Some("Response due".to_string()),
+            reminder_days: vec![5],
+            computed_from: Some(trigger_id.clone()),
+            task_id: None,
+            save: true,
+        }),
+    )
+    .await
+    .expect("compute+save should succeed");
+    let saved = computed.saved.expect("saved deadline should be returned");
+    assert_eq!(saved.rule_ref.as_deref(), Some("FRCP 12(a)(1)"));
+
+    let dependent_id = Uuid::parse_str(&saved.id).expect("dependent uuid");
+    let before = store
+        .get_matter_deadline(&state.user_id, "demo", dependent_id)
+        .await
+        .expect("load dependent deadline before patch")
+        .expect("dependent deadline exists");
+
+    let new_trigger_due_at = trigger_due_at + chrono::TimeDelta::days(1);
+    let expected_after_due_at = crate::legal::calendar::apply_rule(&rule, new_trigger_due_at);
+    let Json(_updated_trigger) = matter_deadlines_patch_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(("demo".to_string(), trigger_id)),
+        Json(UpdateMatterDeadlineRequest {
+            title: None,
+            deadline_type: None,
+            due_at: Some(new_trigger_due_at.to_rfc3339()),
+            completed_at: None,
+            reminder_days: None,
+            rule_ref: None,
+            computed_from: None,
+            task_id: None,
+            is_unsupported: None,
+        }),
+    )
+    .await
+    .expect("patch should succeed");
+
+    let after = store
+        .get_matter_deadline(&state.user_id, "demo", dependent_id)
+        .await
+        .expect("load dependent deadline after patch")
+        .expect("dependent deadline exists");
+    assert_ne!(before.due_at, expected_after_due_at);
+    assert_eq!(after.due_at, expected_after_due_at);
+    assert_eq!(
+        after
+            .explanation
+            .as_ref()
+            .and_then(|value| value.get("rule_id"))
+            .and_then(|value| value.as_str()),
+        Some("frcp_12_a_1")
+    );
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_deadline_override_cascades_dependents_and_resyncs_reminders() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
+
+    let rule = crate::legal::calendar::get_court_rule("frcp_12_a_1")
+        .expect("rules should parse")
+        .expect("rule should exist");
+    let mut trigger_due_at = Utc::now() + chrono::TimeDelta::days(120);
+    while crate::legal::calendar::apply_rule(&rule, trigger_due_at).date_naive()
+        == crate::legal::calendar::apply_rule(&rule, trigger_due_at + chrono::TimeDelta::days(1))
+            .date_naive()
+    {
+        trigger_due_at += chrono::TimeDelta::days(1);
+    }
+    let (_status, Json(trigger)) = matter_deadlines_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateMatterDeadlineRequest {
+            title: "Service date".to_string(),
+            deadline_type: "court_date".to_string(),
+            due_at: trigger_due_at.to_rfc3339(),
+            completed_at: None,
+            reminder_days: vec![],
+            rule_ref: None,
+            computed_from: None,
+            task_id: None,
+            is_unsupported: None,
+        }),
+    )
+    .await
+    .expect("create trigger deadline");
+    let trigger_id = trigger.id.clone();
+
+    let Json(computed) = matter_deadlines_compute_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(MatterDeadlineComputeRequest {
+            rule_id: "frcp_12_a_1".to_string(),
+            trigger_date: trigger_due_at.to_rfc3339(),
+            title: Some("Response due".to_string()),
+            reminder_days: vec![7],
+            computed_from: Some(trigger_id.clone()),
+            task_id: None,
+            save: true,
+        }),
+    )
+    .await
+    .expect("compute+save should succeed");
+
+    let dependent = computed.saved.expect("saved deadline should be returned");
+    let dependent_id = Uuid::parse_str(&dependent.id).expect("dependent uuid");
+    let dependent_before = store
+        .get_matter_deadline(&state.user_id, "demo", dependent_id)
+        .await
+        .expect("load dependent deadline before override")
+        .expect("dependent deadline exists");
+
+    let prefix = deadline_reminder_prefix("demo", dependent_id);
+    let routines_before = db.list_routines("test-user").await.expect("list routines");
+    let routine_before = routines_before
+        .into_iter()
+        .find(|routine| routine.name.starts_with(&prefix) && routine.enabled)
+        .expect("dependent reminder routine should exist");
+    let next_fire_before = routine_before
+        .next_fire_at
+        .expect("dependent reminder should have next fire");
+
+    let new_trigger_due_at = trigger_due_at + chrono::TimeDelta::days(1);
+    let expected_after_due_at = crate::legal::calendar::apply_rule(&rule, new_trigger_due_at);
+    let Json(_updated_trigger) = matter_deadline_override_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(("demo".to_string(), trigger_id)),
+        Json(DeadlineOverrideRequest {
+            due_at: new_trigger_due_at.to_rfc3339(),
+            reason: "Stipulated extension".to_string(),
+        }),
+    )
+    .await
+    .expect("override should succeed");
+
+    let dependent_after = store
+        .get_matter_deadline(&state.user_id, "demo", dependent_id)
+        .await
+        .expect("load dependent deadline after override")
+        .expect("dependent deadline exists");
+    assert_ne!(dependent_before.due_at, expected_after_due_at);
+    assert_eq!(dependent_after.due_at, expected_after_due_at);
+
+    let routines_after = db
+        .list_routines("test-user")
+        .await
+        .expect("list routines after override");
+    let routine_after = routines_after
+        .into_iter()
+        .find(|routine| routine.name.starts_with(&prefix) && routine.enabled)
+        .expect("dependent reminder routine should still exist");
+    let next_fire_after = routine_after
+        .next_fire_at
+        .expect("dependent reminder should have next fire after override");
+    assert!(
+        next_fire_after > next_fire_before,
+        "expected rescheduled reminder after override"
+    );
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_deadline_delete_disables_reminder_routines() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+
+    let due_at = (Utc::now() + chrono::TimeDelta::days(10)).to_rfc3339();
+    let (_status, Json(created)) = matter_deadlines_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateMatterDeadlineRequest {
+            title: "Serve discovery requests".to_string(),
+            deadline_type: "discovery_cutoff".to_string(),
+            due_at,
+            completed_at: None,
+            reminder_days: vec![1, 3],
+            rule_ref: None,
+            computed_from: None,
+            task_id: None,
+            is_unsupported: None,
+        }),
+    )
+    .await
+    .expect("create deadline should succeed");
+
+    let deadline_id = Uuid::parse_str(&created.id).expect("deadline uuid");
+    let prefix = deadline_reminder_prefix("demo", deadline_id);
+
+    let before_delete = db
+        .list_routines("test-user")
+        .await
+        .expect("list routines before delete");
+    let active_count = before_delete
+        .iter()
+        .filter(|routine| routine.name.starts_with(&prefix) && routine.enabled)
+        .count();
+    assert_eq!(active_count, 2);
+
+    let status = matter_deadlines_delete_handler(
+        State(state),
+        owner_principal(),
+        Path(("demo".to_string(), created.id.clone())),
+    )
+    .await
+    .expect("delete deadline should succeed");
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let after_delete = db
+        .list_routines("test-user")
+        .await
+        .expect("list routines after delete");
+    let routines: Vec<_> = after_delete
+        .into_iter()
+        .filter(|routine| routine.name.starts_with(&prefix))
+        .collect();
+    assert_eq!(routines.len(), 2);
+    assert!(routines.iter().all(|routine| !routine.enabled));
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_deadline_reminders_text_opted_in_client() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+
+    let due_at = Utc::now() + chrono::TimeDelta::days(10);
+    let create = |title: &str| {
+        Json(CreateMatterDeadlineRequest {
+            title: title.to_string(),
+            deadline_type: "court_date".to_string(),
+            due_at: due_at.to_rfc3339(),
+            completed_at: None,
+            reminder_days: vec![2],
+            rule_ref: None,
+            computed_from: None,
+            task_id: None,
+            is_unsupported: None,
+        })
+    };
+    let (_status, Json(before_consent)) = matter_deadlines_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        create("Status conference"),
+    )
+    .await
+    .expect("create deadline should succeed");
+
+    let matter = db
+        .get_matter_db("test-user", "demo")
+        .await
+        .expect("load matter")
+        .expect("matter exists");
+    db.set_sms_consent(
+        "test-user",
+        &crate::db::SetSmsConsentParams {
+            phone_number: "+15551234567".to_string(),
+            client_id: Some(matter.client_id),
+            status: crate::db::SmsConsentStatus::OptedIn,
+            source: "START".to_string(),
+        },
+    )
+    .await
+    .expect("record consent");
+
+    let (_status, Json(created)) = matter_deadlines_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        create("Mediation"),
+    )
+    .await
+    .expect("create deadline should succeed");
+
+    let routines = db.list_routines("test-user").await.expect("list routines");
+    let no_consent_prefix =
+        deadline_reminder_prefix("demo", Uuid::parse_str(&before_consent.id).unwrap());
+    assert_eq!(
+        routines
+            .iter()
+            .filter(|routine| routine.name.starts_with(&no_consent_prefix))
+            .count(),
+        1
+    );
+
+    let prefix = deadline_reminder_prefix("demo", Uuid::parse_str(&created.id).unwrap());
+    let sms = routines
+        .iter()
+        .find(|routine| routine.name == format!("{prefix}2-sms"))
+        .expect("client SMS reminder routine");
+    assert!(sms.enabled);
+    assert_eq!(sms.notify.channel.as_deref(), Some("sms"));
+    assert_eq!(sms.notify.user, "+15551234567");
+    assert!(!sms.notify.on_failure);
+    let lawyer = routines
+        .iter()
+        .find(|routine| routine.name == format!("{prefix}2"))
+        .expect("lawyer reminder routine");
+    assert!(lawyer.notify.channel.is_none());
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_dashboard_reports_workflow_scorecard() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let today = Utc::now().date_naive();
+    let upcoming = (today + chrono::TimeDelta::days(7)).to_string();
+    let overdue = (today - chrono::TimeDelta::days(2)).to_string();
+
+    workspace
+        .write("matters/demo/drafts/first-brief.md", "Draft body")
+        .await
+        .expect("seed draft");
+    workspace
+        .write(
+            "matters/demo/workflows/intake_checklist.md",
+            "- [x] Engagement confirmed\n- [ ] Conflict memo attached\n",
+        )
+        .await
+        .expect("seed intake checklist");
+    workspace
+        .write(
+            "matters/demo/workflows/review_and_filing_checklist.md",
+            "- [x] Citation format pass complete\n- [ ] Partner sign-off recorded\n",
+        )
+        .await
+        .expect("seed review checklist");
+    workspace
+            .write(
+                "matters/demo/deadlines/calendar.md",
+                &format!(
+                    "| Date | Deadline / Event | Owner | Status | Source |\n|---|---|---|---|---|\n| {overdue} | Serve disclosures | Team | open | docket |\n| {upcoming} | File opposition | Team | open | order |\n"
+                ),
+            )
+            .await
+            .expect("seed deadlines");
+
+    let state = test_gateway_state_with_store_and_workspace(db, workspace);
+    let Json(resp) =
+        matter_dashboard_handler(State(state), owner_principal(), Path("demo".to_string()))
+            .await
+            .expect("dashboard handler should succeed");
+
+    assert_eq!(resp.matter_id, "demo");
+    assert_eq!(resp.template_count, 2);
+    assert_eq!(resp.draft_count, 1);
+    assert_eq!(resp.checklist_completed, 2);
+    assert_eq!(resp.checklist_total, 4);
+    assert_eq!(resp.overdue_deadlines, 1);
+    assert_eq!(resp.upcoming_deadlines_14d, 1);
+    assert_eq!(
+        resp.next_deadline.as_ref().map(|item| item.date.as_str()),
+        Some(upcoming.as_str())
+    );
+    assert!(resp.document_count >= 6);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_storage_reports_usage_and_soft_quota() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    seed_valid_matter(workspace.as_ref(), "demo-2").await;
+    workspace
+        .write("matters/demo-2/discovery/dump.md", &"x".repeat(4096))
+        .await
+        .expect("seed other matter");
+
+    let mut legal = test_legal_config();
+    let state = test_gateway_state_with_store_workspace_and_legal(
+        Arc::clone(&db),
+        Arc::clone(&workspace),
+        legal.clone(),
+    );
+    let Json(unlimited) =
+        matter_storage_handler(State(state), owner_principal(), Path("demo".to_string()))
+            .await
+            .expect("storage handler should succeed");
+    assert_eq!(unlimited.matter_id, "demo");
+    assert!(unlimited.document_count >= 1);
+    assert!(unlimited.document_bytes > 0);
+    assert!(
+        unlimited.document_bytes < 4096,
+        "other matter must not be counted"
+    );
+    assert_eq!(unlimited.version_count, 0);
+    assert_eq!(
+        unlimited.total_bytes,
+        unlimited.document_bytes + unlimited.chunk_bytes
+    );
+    assert_eq!(
+        unlimited.quota.status,
+        crate::legal::storage::QuotaStatus::Unlimited
+    );
+    assert!(unlimited.quota.warning.is_none());
+
+    legal.storage.soft_quota_bytes = Some(unlimited.total_bytes.max(2) - 1);
+    let state = test_gateway_state_with_store_workspace_and_legal(
+        Arc::clone(&db),
+        Arc::clone(&workspace),
+        legal,
+    );
+    let Json(exceeded) = matter_storage_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("storage handler should succeed");
+    assert_eq!(
+        exceeded.quota.status,
+        crate::legal::storage::QuotaStatus::Exceeded
+    );
+    assert!(exceeded.quota.warning.is_some());
+
+    db.set_setting(
+        "test-user",
+        MATTER_ACTIVE_SETTING,
+        &serde_json::Value::String("demo".to_string()),
+    )
+    .await
+    .expect("set active matter");
+    let Json(written) = memory_write_handler(
+        State(state),
+        owner_principal(),
+        HeaderMap::new(),
+        Json(MemoryWriteRequest {
+            path: "notes.md".to_string(),
+            content: "More notes".to_string(),
+        }),
+    )
+    .await
+    .expect("write should still succeed over a soft quota");
+    assert_eq!(written.path, "matters/demo/notes.md");
+    assert!(written.quota_warning.is_some());
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_filing_package_creates_export_index() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    workspace
+        .write(
+            "matters/demo/workflows/intake_checklist.md",
+            "- [x] Intake complete\n",
+        )
+        .await
+        .expect("seed checklist");
+    workspace
+            .write(
+                "matters/demo/deadlines/calendar.md",
+                "| Date | Deadline / Event | Owner | Status | Source |\n|---|---|---|---|---|\n| 2027-01-15 | File status report | Team | open | order |\n",
+            )
+            .await
+            .expect("seed deadlines");
+
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+
+    let (status, Json(resp)) =
+        matter_filing_package_handler(State(state), owner_principal(), Path("demo".to_string()))
+            .await
+            .expect("filing package should be generated");
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(resp.matter_id, "demo");
+    assert!(
+        resp.path
+            .starts_with("matters/demo/exports/filing-package-")
+    );
+
+    let exported = workspace
+        .read(&resp.path)
+        .await
+        .expect("filing package file should exist");
+    assert!(exported.content.contains("# Filing Package Index"));
+    assert!(exported.content.contains("matters/demo/notes.md"));
+    assert!(exported.content.contains("Template Inventory"));
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_citation_workflow_requires_ready_filing_document_before_export() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
+
+    let written = workspace
+        .write(
+            "matters/demo/filings/brief.md",
+            "Draft brief cites Roe v. Wade, 410 U.S. 113 (1973).\n",
+        )
+        .await
+        .expect("seed filing document");
+    let matter_document = store
+        .upsert_matter_document(
+            &state.user_id,
+            "demo",
+            &crate::db::UpsertMatterDocumentParams {
+                memory_document_id: written.id,
+                path: written.path.clone(),
+                display_name: "Draft Brief".to_string(),
+                category: crate::db::MatterDocumentCategory::Filing,
+                readiness_state: Some(crate::db::DocumentReadinessState::Draft),
+            },
+        )
+        .await
+        .expect("link filing document");
+    crate::channels::web::server::backfill_matter_documents_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("backfill matter documents");
+    let docs = store
+        .list_matter_documents_db(&state.user_id, "demo")
+        .await
+        .expect("list matter documents");
+    let blocking_doc = docs
+        .iter()
+        .find(|doc| doc.id == matter_document.id)
+        .expect("filing document should be present in db");
+    assert_eq!(
+        blocking_doc.category,
+        crate::db::MatterDocumentCategory::Filing
+    );
+    assert_eq!(
+        blocking_doc.readiness_state,
+        crate::db::DocumentReadinessState::Draft
+    );
+
+    let err = matter_filing_package_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect_err("unready filing document should block export");
+    assert_eq!(err.0, StatusCode::CONFLICT);
+    assert!(err.1.contains("not ready to file"));
+
+    let Json(verification) = matter_citations_verify_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(VerifyMatterCitationsRequest {
+            matter_document_id: matter_document.id.to_string(),
+            waivers: vec![CitationWaiverRequest {
+                citation_text: "410 U.S. 113 (1973)".to_string(),
+                waived_by: "Lead".to_string(),
+                reason: "Manual cite verification documented separately".to_string(),
+            }],
+        }),
+    )
+    .await
+    .expect("citation verification should succeed");
+    assert_eq!(verification.readiness_state, "waived");
+    assert_eq!(verification.results.len(), 1);
+    assert_eq!(verification.results[0].status, "waived");
+
+    let Json(citations) = document_citations_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(matter_document.id.to_string()),
+    )
+    .await
+    .expect("document citations should load");
+    assert_eq!(citations.readiness_state, "waived");
+    assert_eq!(citations.results.len(), 1);
+
+    let Json(ready) = document_ready_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(matter_document.id.to_string()),
+        Json(MarkDocumentReadyRequest {
+            attorney: Some("Lead".to_string()),
+        }),
+    )
+    .await
+    .expect("ready-to-file transition should succeed");
+    assert_eq!(
+        ready.document.readiness_state.as_deref(),
+        Some("ready_to_file")
+    );
+
+    let (status, Json(resp)) = matter_filing_package_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("filing package should succeed once document is ready");
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(resp.path.contains("filing-package-"));
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_filing_validation_reports_jurisdiction_rule_failures() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    workspace
+        .write(
+            "matters/demo/matter.yaml",
+            "matter_id: demo\nclient: Demo Client\nteam:\n  - Lead Counsel\n\
+             confidentiality: attorney-client-privileged\nadversaries:\n  - Example Co\n\
+             retention: follow-firm-policy\njurisdiction: ON\n",
+        )
+        .await
+        .expect("seed ontario matter");
+    workspace
+        .write(
+            "matters/demo/exhibits/exhibit_1.md",
+            "Lease dated 2024-01-01.\n",
+        )
+        .await
+        .expect("seed exhibit");
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
+    let written = workspace
+        .write(
+            "matters/demo/filings/factum.md",
+            "---\nfont_size: 12\nmargins: 1\n---\n# Overview\n\nSee Exhibit 1 and Exhibit 3.\n\n# Facts\n",
+        )
+        .await
+        .expect("seed factum");
+    store
+        .upsert_matter_document(
+            &state.user_id,
+            "demo",
+            &crate::db::UpsertMatterDocumentParams {
+                memory_document_id: written.id,
+                path: written.path.clone(),
+                display_name: "Factum".to_string(),
+                category: crate::db::MatterDocumentCategory::Filing,
+                readiness_state: Some(crate::db::DocumentReadinessState::ReadyToFile),
+            },
+        )
+        .await
+        .expect("link factum");
+
+    let Json(resp) = matter_filing_validation_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("validation should run");
+    assert_eq!(resp.jurisdiction.as_deref(), Some("ON"));
+    assert_eq!(resp.report.ruleset_id, "ontario");
+    assert!(!resp.report.passed);
+    let factum = resp
+        .report
+        .documents
+        .iter()
+        .find(|doc| doc.path == "matters/demo/filings/factum.md")
+        .expect("factum should be validated");
+    assert_eq!(factum.issues.len(), 1);
+    assert_eq!(factum.issues[0].check, "required_sections");
+    assert!(
+        factum.issues[0]
+            .message
+            .ends_with("issues, law, order requested")
+    );
+    let package_messages: Vec<&str> = resp
+        .report
+        .package_issues
+        .iter()
+        .map(|issue| issue.message.as_str())
+        .collect();
+    assert!(package_messages.contains(&"Exhibit sequence skips 2"));
+    assert!(
+        package_messages
+            .iter()
+            .any(|message| message.starts_with("No proof of service"))
+    );
+
+    let (status, Json(package)) = matter_filing_package_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("validation failures should not block the package");
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(package.validation_passed, Some(false));
+    let exported = workspace
+        .read(&package.path)
+        .await
+        .expect("filing package file should exist");
+    assert!(exported.content.contains("## Pre-filing Validation"));
+    assert!(
+        exported
+            .content
+            .contains("Rules: Ontario courts (`ontario`)")
+    );
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_exhibits_number_per_party_and_stay_stable_across_packages() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    for name in ["lease", "invoice", "letter", "photo"] {
+        workspace
+            .write(
+                &format!("matters/demo/evidence/{name}.md"),
+                &format!("{name} evidence\n"),
+            )
+            .await
+            .expect("seed evidence");
+    }
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    let mark = |path: &str, prefix: Option<&str>| MarkExhibitRequest {
+        document_id: None,
+        path: Some(format!("matters/demo/evidence/{path}.md")),
+        party_prefix: prefix.map(str::to_string),
+        description: None,
+    };
+
+    let mut labels = Vec::new();
+    for (path, prefix) in [
+        ("lease", None),
+        ("invoice", Some("p")),
+        ("letter", Some("D")),
+    ] {
+        let (status, Json(resp)) = matter_exhibit_mark_handler(
+            State(Arc::clone(&state)),
+            owner_principal(),
+            Path("demo".to_string()),
+            Json(mark(path, prefix)),
+        )
+        .await
+        .expect("exhibit should be marked");
+        assert_eq!(status, StatusCode::CREATED);
+        labels.push(resp.exhibit.label.clone());
+        if path == "lease" {
+            let cover = workspace
+                .read(&resp.exhibit.cover_sheet_path)
+                .await
+                .expect("cover sheet written");
+            assert_eq!(
+                resp.exhibit.cover_sheet_path,
+                "matters/demo/exhibits/covers/exhibit_p-1.md"
+            );
+            assert!(cover.content.contains("PLAINTIFF'S EXHIBIT"));
+        }
+    }
+    assert_eq!(labels, vec!["P-1", "P-2", "D-1"]);
+
+    let err = matter_exhibit_mark_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(mark("lease", Some("D"))),
+    )
+    .await
+    .expect_err("a marked document cannot be marked twice");
+    assert_eq!(err.0, StatusCode::CONFLICT);
+    assert!(err.1.contains("Exhibit P-1"));
+
+    let Json(listed) = matter_exhibits_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("exhibits should list");
+    let invoice = listed
+        .exhibits
+        .iter()
+        .find(|exhibit| exhibit.label == "P-2")
+        .expect("P-2 listed");
+    let Json(withdrawn) = matter_exhibit_withdraw_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(("demo".to_string(), invoice.id.clone())),
+    )
+    .await
+    .expect("exhibit should be withdrawn");
+    assert_eq!(withdrawn.exhibit.status, "withdrawn");
+
+    let (_, Json(photo)) = matter_exhibit_mark_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(mark("photo", None)),
+    )
+    .await
+    .expect("exhibit should be marked");
+    assert_eq!(photo.exhibit.label, "P-3");
+
+    let list = workspace
+        .read("matters/demo/exhibits/exhibit_list.md")
+        .await
+        .expect("exhibit list written");
+    assert!(list.content.contains("| P-2 |"));
+    assert!(list.content.contains("Withdrawn"));
+
+    let mut exhibit_sections = Vec::new();
+    for _ in 0..2 {
+        let (_, Json(package)) = matter_filing_package_handler(
+            State(Arc::clone(&state)),
+            owner_principal(),
+            Path("demo".to_string()),
+        )
+        .await
+        .expect("filing package should be generated");
+        let exported = workspace
+            .read(&package.path)
+            .await
+            .expect("filing package file should exist");
+        let start = exported
+            .content
+            .find("## Exhibit List")
+            .expect("package lists exhibits");
+        let end = exported.content[start..]
+            .find("## Pre-filing Validation")
+            .map_or(exported.content.len(), |offset| start + offset);
+        exhibit_sections.push(exported.content[start..end].to_string());
+    }
+    assert_eq!(exhibit_sections[0], exhibit_sections[1]);
+    assert!(
+        exhibit_sections[0]
+            .contains("- Exhibit P-2: `matters/demo/evidence/invoice.md` (withdrawn)")
+    );
+    assert!(exhibit_sections[0].contains("- Exhibit P-3: `matters/demo/evidence/photo.md`"));
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn settlement_proposals_warn_past_authority_and_export_history_without_it() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+
+    let proposal = |kind: &str, side: &str, from: &str, amount: &str, on: &str| {
+        CreateSettlementProposalRequest {
+            kind: kind.to_string(),
+            side: side.to_string(),
+            from_party: from.to_string(),
+            to_party: None,
+            amount: Some(amount.to_string()),
+            terms: None,
+            proposed_on: Some(on.to_string()),
+            expires_on: None,
+        }
+    };
+    let (status, Json(demand)) = settlement_proposal_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(proposal(
+            "demand",
+            "client",
+            "Demo Client",
+            "250000",
+            "2025-01-10",
+        )),
+    )
+    .await
+    .expect("record demand");
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(demand.authority_check.is_none(), "no authority on file yet");
+
+    let (status, Json(authority)) = settlement_authority_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateSettlementAuthorityRequest {
+            kind: "minimum".to_string(),
+            amount: "150000".to_string(),
+            granted_on: Some("2025-02-15".to_string()),
+            granted_by: Some("Demo Client CFO".to_string()),
+            notes: None,
+        }),
+    )
+    .await
+    .expect("record authority");
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(authority.amount, "150000");
+
+    let (_, Json(offer)) = settlement_proposal_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(proposal(
+            "offer",
+            "opposing",
+            "Example Co",
+            "60000",
+            "2025-03-01",
+        )),
+    )
+    .await
+    .expect("record offer");
+    assert!(
+        offer.authority_check.is_none(),
+        "opposing offers are not checked"
+    );
+
+    let Json(check) = settlement_authority_check_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(SettlementAuthorityCheckRequest {
+            amount: "140000".to_string(),
+        }),
+    )
+    .await
+    .expect("check");
+    let check = check.check.expect("authority on file");
+    assert!(!check.within_authority);
+    assert!(
+        check
+            .warning
+            .unwrap()
+            .contains("below the client's minimum")
+    );
+
+    let (_, Json(counter)) = settlement_proposal_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(proposal(
+            "demand",
+            "client",
+            "Demo Client",
+            "140000",
+            "2025-03-05",
+        )),
+    )
+    .await
+    .expect("over-authority demand is still recorded");
+    let counter_check = counter.authority_check.expect("checked");
+    assert!(!counter_check.within_authority);
+    assert_eq!(counter_check.excess, rust_decimal::Decimal::from(10_000));
+
+    let Json(updated) = settlement_proposal_status_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(("demo".to_string(), offer.proposal.id.clone())),
+        Json(UpdateSettlementProposalStatusRequest {
+            status: "countered".to_string(),
+            response_notes: Some("Countered at 140k".to_string()),
+        }),
+    )
+    .await
+    .expect("update status");
+    assert_eq!(updated.status, "countered");
+
+    let Json(settlement) = matter_settlement_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("settlement");
+    assert_eq!(settlement.proposals.len(), 3);
+    assert_eq!(
+        settlement.position.gap,
+        Some(rust_decimal::Decimal::from(80_000))
+    );
+    assert_eq!(
+        settlement.authority.as_ref().map(|a| a.kind.as_str()),
+        Some("minimum")
+    );
+
+    let err = settlement_proposal_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateSettlementProposalRequest {
+            amount: None,
+            ..proposal("offer", "opposing", "Example Co", "1", "2025-03-10")
+        }),
+    )
+    .await
+    .expect_err("amount or terms required");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+
+    let (status, Json(export)) = settlement_history_export_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("export");
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(
+        export.path,
+        "matters/demo/settlement/negotiation_history.md"
+    );
+    assert_eq!(export.proposal_count, 3);
+    let doc = workspace.read(&export.path).await.expect("read history");
+    assert!(
+        doc.content
+            .contains("| Demand | $140,000.00 | -$110,000.00 |")
+    );
+    assert!(doc.content.contains("- Gap: $80,000.00"));
+    assert!(
+        !doc.content.contains("150,000"),
+        "authority must not appear in the exhibit"
+    );
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_timeline_merges_sources_in_date_order() {
+    use crate::db::{CreateMatterDeadlineParams, MatterDeadlineType};
+
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    workspace
+        .write(
+            "matters/demo/deadlines/calendar.md",
+            "| Date | Deadline / Event | Owner | Status | Source |\n|---|---|---|---|---|\n\
+             | 2024-02-01 | Case management conference | Team | open | docket |\n\
+             | 2024-03-01 | File opposition | Team | open | order |\n",
+        )
+        .await
+        .expect("seed docket");
+    workspace
+        .write(
+            "matters/demo/communications/contact_log.md",
+            &format!(
+                "{}| 2024-01-10 | Opposing counsel | Phone | Discussed extension |  |\n",
+                crate::legal::correspondence::CONTACT_LOG_TEMPLATE
+            ),
+        )
+        .await
+        .expect("seed contact log");
+    workspace
+        .write("matters/demo/evidence/photo.md", "Scene photo\n")
+        .await
+        .expect("seed evidence");
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("matter row");
+    db.create_matter_fact(
+        &state.user_id,
+        "demo",
+        &crate::db::CreateMatterFactParams {
+            fact_date: chrono::NaiveDate::from_ymd_opt(2024, 1, 5).unwrap(),
+            description: "Collision at Main and 3rd".to_string(),
+            source_path: "matters/demo/evidence/photo.md".to_string(),
+            source_locator: Some("line 1".to_string()),
+            source_excerpt: None,
+            confidence: 0.9,
+            extractor: "heuristic".to_string(),
+            created_by: state.user_id.clone(),
+        },
+    )
+    .await
+    .expect("create fact");
+    db.create_matter_deadline(
+        &state.user_id,
+        "demo",
+        &CreateMatterDeadlineParams {
+            title: "File opposition".to_string(),
+            deadline_type: MatterDeadlineType::Filing,
+            due_at: "2024-03-01T17:00:00Z".parse().unwrap(),
+            completed_at: None,
+            reminder_days: Vec::new(),
+            rule_ref: Some("FRCP 6".to_string()),
+            computed_from: None,
+            task_id: None,
+            explanation: None,
+            rule_version: None,
+            is_unsupported: false,
+        },
+    )
+    .await
+    .expect("create deadline");
+
+    let Json(resp) = matter_timeline_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Query(MatterTimelineQuery::default()),
+    )
+    .await
+    .expect("timeline");
+    let dated: Vec<(String, &str)> = resp
+        .events
+        .iter()
+        .filter(|event| event.date.to_string().starts_with("2024-"))
+        .map(|event| (event.date.to_string(), event.event_type.as_str()))
+        .collect();
+    assert_eq!(
+        dated,
+        vec![
+            ("2024-01-05".to_string(), "fact"),
+            ("2024-01-10".to_string(), "communication"),
+            ("2024-02-01".to_string(), "docket"),
+            ("2024-03-01".to_string(), "deadline"),
+        ],
+        "docket row mirrored by a deadline is listed once: {:?}",
+        resp.events
+    );
+    assert!(
+        resp.events.iter().any(|event| {
+            event.event_type.as_str() == "document"
+                && event.source == "matters/demo/evidence/photo.md"
+        }),
+        "evidence file listed as a document event"
+    );
+    let json = serde_json::to_value(&resp.events[0]).expect("serialize");
+    assert_eq!(json["type"], "fact");
+    assert_eq!(json["source"], "matters/demo/evidence/photo.md");
+
+    let Json(filtered) = matter_timeline_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Query(MatterTimelineQuery {
+            from: None,
+            to: Some("2024-01-31".to_string()),
+            types: Some("facts,communication".to_string()),
+        }),
+    )
+    .await
+    .expect("filtered timeline");
+    assert_eq!(filtered.events.len(), 2);
+    let err = matter_timeline_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Query(MatterTimelineQuery {
+            types: Some("emails".to_string()),
+            ..Default::default()
+        }),
+    )
+    .await
+    .expect_err("unknown type");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+
+    let (status, Json(export)) = matter_timeline_export_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Query(MatterTimelineQuery::default()),
+    )
+    .await
+    .expect("export");
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(export.path.starts_with("matters/demo/exports/timeline-"));
+    let doc = workspace.read(&export.path).await.expect("read export");
+    assert!(
+        doc.content
+            .contains("| 2024-02-01 | Docket | Case management conference |")
+    );
+
+    let Json(after) = matter_timeline_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Query(MatterTimelineQuery::default()),
+    )
+    .await
+    .expect("timeline after export");
+    assert!(
+        after
+            .events
+            .iter()
+            .all(|event| !event.source.contains("/exports/"))
+    );
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_translation_certification_workflow() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    let store = state.store.as_ref().expect("store should exist");
+
+    workspace
+        .write("matters/demo/exhibits/contrato.md", "Las partes acuerdan.")
+        .await
+        .expect("seed source");
+    workspace
+        .write(
+            "matters/demo/exhibits/contrato.en.machine-translation.md",
+            "The parties agree.",
+        )
+        .await
+        .expect("seed machine translation");
+    let translation = store
+        .upsert_document_translation(
+            &state.user_id,
+            "demo",
+            &crate::db::UpsertDocumentTranslationParams {
+                source_path: "matters/demo/exhibits/contrato.md".to_string(),
+                source_language: Some("es".to_string()),
+                target_language: "en".to_string(),
+                translation_path: "matters/demo/exhibits/contrato.en.machine-translation.md"
+                    .to_string(),
+                provider: "test-model".to_string(),
+                created_by: state.user_id.clone(),
+            },
+        )
+        .await
+        .expect("record translation");
+    let path = || Path(("demo".to_string(), translation.id.to_string()));
+
+    let Json(list) = matter_translations_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("list translations");
+    assert_eq!(list.translations.len(), 1);
+    assert_eq!(list.translations[0].status, "machine_draft");
+
+    let Json(requested) = matter_translation_request_certification_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        path(),
+        Json(RequestTranslationCertificationRequest {
+            translator: Some("Ana Translator".to_string()),
+        }),
+    )
+    .await
+    .expect("request certification");
+    assert_eq!(requested.translation.status, "certification_requested");
+    assert_eq!(
+        requested.translation.translator.as_deref(),
+        Some("Ana Translator")
+    );
+
+    let err = matter_translation_certify_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        path(),
+        Json(CertifyTranslationRequest {
+            certified_path: "matters/demo/exhibits/contrato.en.certified.md".to_string(),
+            translator: "Ana Translator".to_string(),
+        }),
+    )
+    .await
+    .expect_err("missing certified file should be rejected");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+
+    workspace
+        .write(
+            "matters/demo/exhibits/contrato.en.certified.md",
+            "The parties hereby agree.",
+        )
+        .await
+        .expect("seed certified translation");
+    let Json(certified) = matter_translation_certify_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        path(),
+        Json(CertifyTranslationRequest {
+            certified_path: "matters/demo/exhibits/contrato.en.certified.md".to_string(),
+            translator: "Ana Translator".to_string(),
+        }),
+    )
+    .await
+    .expect("certify translation");
+    assert_eq!(certified.translation.status, "certified");
+    assert!(certified.translation.certified_at.is_some());
+
+    let Json(detail) =
+        matter_translation_detail_handler(State(Arc::clone(&state)), owner_principal(), path())
+            .await
+            .expect("side-by-side view");
+    assert_eq!(
+        detail.source_content.as_deref(),
+        Some("Las partes acuerdan.")
+    );
+    assert_eq!(
+        detail.machine_translation_content.as_deref(),
+        Some("The parties agree.")
+    );
+    assert_eq!(
+        detail.certified_content.as_deref(),
+        Some("The parties hereby agree.")
+    );
+
+    let err = matter_translation_request_certification_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        path(),
+        Json(RequestTranslationCertificationRequest { translator: None }),
+    )
+    .await
+    .expect_err("certified translations cannot re-enter the workflow");
+    assert_eq!(err.0, StatusCode::CONFLICT);
+
+    // Re-running the machine translation refreshes the draft but keeps the
+    // certification on record.
+    let rerun = store
+        .upsert_document_translation(
+            &state.user_id,
+            "demo",
+            &crate::db::UpsertDocumentTranslationParams {
+                source_path: "matters/demo/exhibits/contrato.md".to_string(),
+                source_language: Some("es".to_string()),
+                target_language: "en".to_string(),
+                translation_path: "matters/demo/exhibits/contrato.en.machine-translation.md"
+                    .to_string(),
+                provider: "other-model".to_string(),
+                created_by: state.user_id.clone(),
+            },
+        )
+        .await
+        .expect("re-record translation");
+    assert_eq!(rerun.id, translation.id);
+    assert_eq!(rerun.status, crate::db::TranslationStatus::Certified);
+    assert_eq!(rerun.provider, "other-model");
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn client_status_report_requires_approval_before_sending() {
+    use crate::tools::ToolRegistry;
+
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let sent = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let registry = ToolRegistry::new();
+    registry.register_sync(Arc::new(RecordingEmailTool::new(Arc::clone(&sent))));
+    let Ok(mut inner) = Arc::try_unwrap(test_gateway_state_with_store_and_workspace(
+        Arc::clone(&db),
+        Arc::clone(&workspace),
+    )) else {
+        panic!("fresh state should be uniquely owned");
+    };
+    inner.tool_registry = Some(Arc::new(registry));
+    let state = Arc::new(inner);
+    let store = state.store.as_ref().expect("store should exist");
+
+    let mut reports = Vec::new();
+    for date in ["2026-10-05", "2026-10-12"] {
+        let report_path = format!("matters/demo/communications/status-reports/{date}.md");
+        workspace
+            .write(&report_path, "# Status Update\n\nDiscovery is on track.\n")
+            .await
+            .expect("seed report");
+        reports.push(
+            store
+                .upsert_client_status_report(
+                    &state.user_id,
+                    "demo",
+                    &crate::db::UpsertClientStatusReportParams {
+                        report_path,
+                        subject: "Status update: demo".to_string(),
+                        recipient: None,
+                        created_by: state.user_id.clone(),
+                    },
+                )
+                .await
+                .expect("queue report"),
+        );
+    }
+    let path = |id: Uuid| Path(("demo".to_string(), id.to_string()));
+
+    let Json(list) = matter_status_reports_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("list reports");
+    assert_eq!(list.reports.len(), 2);
+    assert!(list.reports.iter().all(|r| r.status == "pending_approval"));
+    assert!(sent.lock().expect("sent lock").is_empty());
+
+    let err = matter_status_report_approve_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        path(reports[0].id),
+        Json(ApproveClientStatusReportRequest {
+            attorney: "Lead Counsel".to_string(),
+            note: None,
+            recipient: None,
+        }),
+    )
+    .await
+    .expect_err("approval without a recipient should fail");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+
+    let Json(approved) = matter_status_report_approve_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        path(reports[0].id),
+        Json(ApproveClientStatusReportRequest {
+            attorney: "Lead Counsel".to_string(),
+            note: Some("Looks good".to_string()),
+            recipient: Some("client@example.com".to_string()),
+        }),
+    )
+    .await
+    .expect("approve and send");
+    assert_eq!(approved.report.status, "sent");
+    assert_eq!(approved.report.reviewed_by.as_deref(), Some("Lead Counsel"));
+    assert!(approved.report.sent_at.is_some());
+    {
+        let sent = sent.lock().expect("sent lock");
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0]["to"], "client@example.com");
+        assert!(
+            sent[0]["body"]
+                .as_str()
+                .is_some_and(|b| b.contains("Discovery is on track."))
+        );
+    }
+
+    let err = matter_status_report_approve_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        path(reports[0].id),
+        Json(ApproveClientStatusReportRequest {
+            attorney: "Lead Counsel".to_string(),
+            note: None,
+            recipient: None,
+        }),
+    )
+    .await
+    .expect_err("sent reports cannot be approved again");
+    assert_eq!(err.0, StatusCode::CONFLICT);
+
+    let Json(rejected) = matter_status_report_reject_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        path(reports[1].id),
+        Json(RejectClientStatusReportRequest {
+            attorney: "Lead Counsel".to_string(),
+            note: "Hold until the settlement call".to_string(),
+        }),
+    )
+    .await
+    .expect("reject");
+    assert_eq!(rejected.report.status, "rejected");
+    assert_eq!(sent.lock().expect("sent lock").len(), 1);
+
+    let Json(detail) = matter_status_report_detail_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        path(reports[1].id),
+    )
+    .await
+    .expect("detail");
+    assert_eq!(
+        detail.report.review_note.as_deref(),
+        Some("Hold until the settlement call")
+    );
+    assert!(
+        detail
+            .content
+            .is_some_and(|c| c.contains("Discovery is on track."))
+    );
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn client_communication_preferences_gate_status_report_email() {
+    use crate::tools::ToolRegistry;
+
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let sent = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let registry = ToolRegistry::new();
+    registry.register_sync(Arc::new(RecordingEmailTool::new(Arc::clone(&sent))));
+    let Ok(mut inner) = Arc::try_unwrap(test_gateway_state_with_store_and_workspace(
+        Arc::clone(&db),
+        Arc::clone(&workspace),
+    )) else {
+        panic!("fresh state should be uniquely owned");
+    };
+    inner.tool_registry = Some(Arc::new(registry));
+    let state = Arc::new(inner);
+    let store = state.store.as_ref().expect("store should exist");
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("matter row");
+    let client_id = store
+        .get_matter_db(&state.user_id, "demo")
+        .await
+        .expect("get matter")
+        .expect("matter exists")
+        .client_id
+        .to_string();
+
+    let report_path = "matters/demo/communications/status-reports/2026-10-12.md".to_string();
+    workspace
+        .write(&report_path, "# Status Update\n\nDiscovery is on track.\n")
+        .await
+        .expect("seed report");
+    let report = store
+        .upsert_client_status_report(
+            &state.user_id,
+            "demo",
+            &crate::db::UpsertClientStatusReportParams {
+                report_path,
+                subject: "Status update: demo".to_string(),
+                recipient: Some("client@example.com".to_string()),
+                created_by: state.user_id.clone(),
+            },
+        )
+        .await
+        .expect("queue report");
+    let approve = || {
+        matter_status_report_approve_handler(
+            State(Arc::clone(&state)),
+            owner_principal(),
+            Path(("demo".to_string(), report.id.to_string())),
+            Json(ApproveClientStatusReportRequest {
+                attorney: "Lead Counsel".to_string(),
+                note: None,
+                recipient: None,
+            }),
+        )
+    };
+
+    let err = client_communications_put_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(client_id.clone()),
+        Json(UpdateClientCommunicationPreferencesRequest {
+            allowed_channels: vec!["email".to_string(), "fax".to_string()],
+            quiet_hours: Vec::new(),
+            timezone: None,
+            language: None,
+            disclaimers: Vec::new(),
+        }),
+    )
+    .await
+    .expect_err("unknown channels are rejected");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+
+    let Json(saved) = client_communications_put_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(client_id.clone()),
+        Json(UpdateClientCommunicationPreferencesRequest {
+            allowed_channels: vec!["Email".to_string()],
+            quiet_hours: Vec::new(),
+            timezone: Some("America/Chicago".to_string()),
+            language: Some("es".to_string()),
+            disclaimers: vec![
+                "Privileged and confidential attorney-client communication.".to_string(),
+            ],
+        }),
+    )
+    .await
+    .expect("save preferences");
+    let prefs = saved.preferences.expect("preferences saved");
+    assert_eq!(prefs.allowed_channels, vec!["email".to_string()]);
+    assert!(saved.consents.is_empty());
+
+    // Preferences without a recorded consent hold the email.
+    let err = approve().await.expect_err("no consent on record");
+    assert_eq!(err.0, StatusCode::CONFLICT);
+    assert!(err.1.contains("no email consent"));
+    assert!(sent.lock().expect("sent lock").is_empty());
+    let pending = store
+        .get_client_status_report(&state.user_id, "demo", report.id)
+        .await
+        .expect("get report")
+        .expect("report exists");
+    assert_eq!(
+        pending.status,
+        crate::db::ClientStatusReportStatus::PendingApproval
+    );
+
+    let (status, Json(recorded)) = client_consent_record_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(client_id.clone()),
+        Json(RecordClientConsentRequest {
+            channel: "email".to_string(),
+            status: "granted".to_string(),
+            source: "Engagement letter".to_string(),
+            consented_at: Some("2026-01-05".to_string()),
+        }),
+    )
+    .await
+    .expect("record consent");
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(recorded.consents.len(), 1);
+    assert_eq!(recorded.consents[0].status, "granted");
+    assert!(recorded.consents[0].consented_at.starts_with("2026-01-05"));
+
+    let Json(approved) = approve().await.expect("approve and send");
+    assert_eq!(approved.report.status, "sent");
+    {
+        let sent = sent.lock().expect("sent lock");
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0]["body"].as_str().is_some_and(|body| {
+            body.contains("Discovery is on track.")
+                && body.ends_with("Privileged and confidential attorney-client communication.")
+        }));
+    }
+
+    let _ = client_consent_record_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(client_id.clone()),
+        Json(RecordClientConsentRequest {
+            channel: "email".to_string(),
+            status: "revoked".to_string(),
+            source: "Client phone call".to_string(),
+            consented_at: None,
+        }),
+    )
+    .await
+    .expect("record revocation");
+    let Json(current) =
+        client_communications_get_handler(State(Arc::clone(&state)), Path(client_id))
+            .await
+            .expect("get preferences");
+    assert_eq!(current.consents.len(), 1);
+    assert_eq!(current.consents[0].status, "revoked");
+    assert_eq!(current.history.len(), 2);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn documents_generate_uses_client_language_variant_and_locale_formatting() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    let store = state.store.as_ref().expect("store should exist");
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("matter row");
+    let client_id = store
+        .get_matter_db(&state.user_id, "demo")
+        .await
+        .expect("get matter")
+        .expect("matter exists")
+        .client_id;
+
+    let base = store
+        .upsert_document_template(
+            &state.user_id,
+            &crate::db::UpsertDocumentTemplateParams {
+                matter_id: None,
+                name: "fee_letter.md".to_string(),
+                body: "Fee {{ extra.fee | currency }} due {{ extra.due | legal_date }}".to_string(),
+                variables_json: serde_json::json!([]),
+            },
+        )
+        .await
+        .expect("base template");
+    store
+        .upsert_document_template(
+            &state.user_id,
+            &crate::db::UpsertDocumentTemplateParams {
+                matter_id: None,
+                name: "fee_letter.es.md".to_string(),
+                body: "Honorarios {{ extra.fee | currency(symbol=\"US$\") }} con vencimiento \
+                       el {{ extra.due | legal_date }}"
+                    .to_string(),
+                variables_json: serde_json::json!([]),
+            },
+        )
+        .await
+        .expect("spanish variant");
+    let _ = client_communications_put_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(client_id.to_string()),
+        Json(UpdateClientCommunicationPreferencesRequest {
+            allowed_channels: vec!["email".to_string()],
+            quiet_hours: Vec::new(),
+            timezone: None,
+            language: Some("es".to_string()),
+            disclaimers: Vec::new(),
+        }),
+    )
+    .await
+    .expect("save preferences");
+
+    let generate = |locale: Option<&str>| {
+        documents_generate_handler(
+            State(Arc::clone(&state)),
+            owner_principal(),
+            Json(GenerateDocumentRequest {
+                template_id: base.id.to_string(),
+                matter_id: "demo".to_string(),
+                extra: serde_json::json!({"fee": 2500, "due": "2026-11-02"}),
+                display_name: None,
+                category: None,
+                label: None,
+                locale: locale.map(str::to_string),
+            }),
+        )
+    };
+    let (_, Json(spanish)) = generate(None).await.expect("generate for client language");
+    assert_eq!(spanish.locale, "es");
+    assert_eq!(spanish.template_name, "fee_letter.es.md");
+    assert_eq!(
+        workspace.read(&spanish.path).await.expect("read").content,
+        "Honorarios 2.500,00\u{a0}US$ con vencimiento el 2 de noviembre de 2026"
+    );
+
+    let (_, Json(english)) = generate(Some("en")).await.expect("generate in english");
+    assert_eq!(english.template_name, "fee_letter.md");
+    assert_eq!(
+        workspace.read(&english.path).await.expect("read").content,
+        "Fee $2,500.00 due November 2, 2026"
+    );
+
+    let err = generate(Some("klingon")).await.expect_err("unknown locale");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn list_matters_root_entries_returns_500_for_storage_errors() {
+    let err = list_matters_root_entries(Err(crate::error::WorkspaceError::SearchFailed {
+        reason: "boom".to_string(),
+    }))
+    .expect_err("search errors should map to 500");
+    assert_eq!(err.0, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(err.1.contains("Search failed"));
+}
+
+#[test]
+fn list_matters_root_entries_allows_document_not_found_as_empty() {
+    let entries = list_matters_root_entries(Err(crate::error::WorkspaceError::DocumentNotFound {
+        doc_type: MATTER_ROOT.to_string(),
+        user_id: "test-user".to_string(),
+    }))
+    .expect("missing matter root should be treated as empty");
+    assert!(entries.is_empty());
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_invoices_list_returns_recent_limited_rows() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
+
+    for idx in 1..=3 {
+        let invoice_number = format!("INV-LIST-{idx:03}");
+        let params = crate::db::CreateInvoiceParams {
+            matter_id: "demo".to_string(),
+            invoice_number,
+            status: crate::db::InvoiceStatus::Draft,
+            issued_date: None,
+            due_date: None,
+            subtotal: rust_decimal::Decimal::ZERO,
+            tax: rust_decimal::Decimal::ZERO,
+            total: rust_decimal::Decimal::ZERO,
+            paid_amount: rust_decimal::Decimal::ZERO,
+            notes: Some("List test".to_string()),
+        };
+        store
+            .save_invoice_draft(&state.user_id, &params, &[])
+            .await
+            .expect("save invoice draft");
+    }
+
+    let Json(resp) = matter_invoices_list_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Query(MatterInvoicesQuery { limit: Some(2) }),
+    )
+    .await
+    .expect("matter invoices should load");
+
+    assert_eq!(resp.matter_id, "demo");
+    assert_eq!(resp.invoices.len(), 2);
+    let invoice_numbers: std::collections::HashSet<&str> = resp
+        .invoices
+        .iter()
+        .map(|invoice| invoice.invoice_number.as_str())
+        .collect();
+    assert_eq!(invoice_numbers.len(), 2);
+    assert!(invoice_numbers.is_subset(&std::collections::HashSet::from([
+        "INV-LIST-001",
+        "INV-LIST-002",
+        "INV-LIST-003",
+    ])));
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_invoices_list_rejects_invalid_limit_values() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+
+    let err = matter_invoices_list_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Query(MatterInvoicesQuery { limit: Some(0) }),
+    )
+    .await
+    .expect_err("limit=0 should fail");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+
+    let err = matter_invoices_list_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Query(MatterInvoicesQuery { limit: Some(101) }),
+    )
+    .await
+    .expect_err("limit above max should fail");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_invoices_list_blocks_non_owner_access() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+
+    // Build a state whose owner is "other-user" — a different user than the
+    // matter owner ("test-user") so the RBAC guard rejects the request.
+    let other_state =
+        test_gateway_state_for_user(Arc::clone(&db), Arc::clone(&workspace), "other-user");
+
+    let err = matter_invoices_list_handler(
+        State(other_state),
+        owner_principal(),
+        Path("demo".to_string()),
+        Query(MatterInvoicesQuery { limit: Some(10) }),
+    )
+    .await
+    .expect_err("non-owner should not access matter invoices");
+    // RBAC guard fires before matter lookup: returns FORBIDDEN (not NOT_FOUND).
+    assert_eq!(err.0, StatusCode::FORBIDDEN);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn invoices_finalize_marks_entries_billed_and_supports_trust_payment() {
+    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
+    crate::legal::audit::clear_test_events();
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
+
+    let time_entry = store
+        .create_time_entry(
+            &state.user_id,
+            "demo",
+            &crate::db::CreateTimeEntryParams {
+                timekeeper: "Lead".to_string(),
+                description: "Motion draft".to_string(),
+                hours: rust_decimal::Decimal::new(150, 2),
+                hourly_rate: Some(rust_decimal::Decimal::new(20000, 2)),
+                task_code: None,
+                activity_code: None,
+                resolved_rate: None,
+                rate_source: None,
+                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 1).expect("valid date"),
+                billable: true,
+                block_billing_flag: false,
+                block_billing_reason: None,
+            },
+        )
+        .await
+        .expect("seed time entry");
+    let expense_entry = store
+        .create_expense_entry(
+            &state.user_id,
+            "demo",
+            &crate::db::CreateExpenseEntryParams {
+                submitted_by: "Lead".to_string(),
+                description: "Filing fee".to_string(),
+                amount: rust_decimal::Decimal::new(4000, 2),
+                category: crate::db::ExpenseCategory::FilingFee,
+                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 1).expect("valid date"),
+                receipt_path: None,
+                billable: true,
+            },
+        )
+        .await
+        .expect("seed expense entry");
+
+    let (created_status, Json(created)) = invoices_save_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(DraftInvoiceRequest {
+            matter_id: "demo".to_string(),
+            invoice_number: "INV-1001".to_string(),
+            due_date: Some("2026-05-30".to_string()),
+            notes: Some("Initial billing cycle".to_string()),
+        }),
+    )
+    .await
+    .expect("save draft should succeed");
+    assert_eq!(created_status, StatusCode::CREATED);
+    assert_eq!(created.invoice.status, "draft");
+    assert_eq!(created.line_items.len(), 2);
+    let invoice_id = created.invoice.id.clone();
+
+    let Json(finalized) = invoices_finalize_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice_id.clone()),
+    )
+    .await
+    .expect("finalize should succeed");
+    assert_eq!(finalized.invoice.status, "sent");
+
+    let invoice_uuid = Uuid::parse_str(&invoice_id).expect("invoice uuid");
+    let time_after = store
+        .get_time_entry(&state.user_id, "demo", time_entry.id)
+        .await
+        .expect("get time entry")
+        .expect("time entry exists");
+    let expense_after = store
+        .get_expense_entry(&state.user_id, "demo", expense_entry.id)
+        .await
+        .expect("get expense entry")
+        .expect("expense entry exists");
+    let invoice_id_str = invoice_uuid.to_string();
+    assert_eq!(
+        time_after.billed_invoice_id.as_deref(),
+        Some(invoice_id_str.as_str())
+    );
+    assert_eq!(
+        expense_after.billed_invoice_id.as_deref(),
+        Some(invoice_id_str.as_str())
+    );
+
+    let (deposit_status, _deposit_body) = matter_trust_deposit_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(TrustDepositRequest {
+            amount: "500.00".to_string(),
+            recorded_by: "Lead".to_string(),
+            description: Some("Retainer deposit".to_string()),
+            reference_number: None,
+        }),
+    )
+    .await
+    .expect("trust deposit should succeed");
+    assert_eq!(deposit_status, StatusCode::CREATED);
+
+    let Json(payment) = invoices_payment_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice_id.clone()),
+        Json(RecordInvoicePaymentRequest {
+            amount: "50.00".to_string(),
+            recorded_by: "Lead".to_string(),
+            draw_from_trust: true,
+            description: Some("Apply trust funds".to_string()),
+        }),
+    )
+    .await
+    .expect("payment should succeed");
+    let paid = payment
+        .invoice
+        .paid_amount
+        .parse::<rust_decimal::Decimal>()
+        .expect("paid amount should parse");
+    assert_eq!(paid, rust_decimal::Decimal::new(5000, 2));
+    assert!(payment.trust_entry.is_some());
+
+    let Json(ledger) =
+        matter_trust_ledger_handler(State(state), owner_principal(), Path("demo".to_string()))
+            .await
+            .expect("ledger should load");
+    let balance = ledger
+        .balance
+        .parse::<rust_decimal::Decimal>()
+        .expect("balance should parse");
+    assert_eq!(balance, rust_decimal::Decimal::new(45000, 2));
+    assert_eq!(ledger.entries.len(), 2);
+
+    let events = crate::legal::audit::test_events_snapshot();
+    assert!(
+        events
+            .iter()
+            .any(|event| event.event_type == "invoice_finalized")
+    );
+    assert!(
+        events
+            .iter()
+            .any(|event| event.event_type == "payment_recorded")
+    );
+    assert!(
+        events
+            .iter()
+            .any(|event| event.event_type == "trust_deposit")
+    );
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn invoices_payment_rejects_trust_overdraw() {
+    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
+    crate::legal::audit::clear_test_events();
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
+
+    store
+        .create_expense_entry(
+            &state.user_id,
+            "demo",
+            &crate::db::CreateExpenseEntryParams {
+                submitted_by: "Lead".to_string(),
+                description: "Service fee".to_string(),
+                amount: rust_decimal::Decimal::new(10000, 2),
+                category: crate::db::ExpenseCategory::Other,
+                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 2).expect("valid date"),
+                receipt_path: None,
+                billable: true,
+            },
+        )
+        .await
+        .expect("seed expense entry");
+
+    let (_status, Json(created)) = invoices_save_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(DraftInvoiceRequest {
+            matter_id: "demo".to_string(),
+            invoice_number: "INV-2001".to_string(),
+            due_date: Some("2026-06-01".to_string()),
+            notes: None,
+        }),
+    )
+    .await
+    .expect("save draft should succeed");
+    let _ = invoices_finalize_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(created.invoice.id.clone()),
+    )
+    .await
+    .expect("finalize should succeed");
+
+    let (_deposit_status, _deposit_body) = matter_trust_deposit_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(TrustDepositRequest {
+            amount: "10.00".to_string(),
+            recorded_by: "Lead".to_string(),
+            description: Some("Small deposit".to_string()),
+            reference_number: None,
+        }),
+    )
+    .await
+    .expect("trust deposit should succeed");
+
+    let result = invoices_payment_handler(
+        State(state),
+        owner_principal(),
+        Path(created.invoice.id),
+        Json(RecordInvoicePaymentRequest {
+            amount: "20.00".to_string(),
+            recorded_by: "Lead".to_string(),
+            draw_from_trust: true,
+            description: Some("Attempt overdraw".to_string()),
+        }),
+    )
+    .await;
+    let err = result.expect_err("overdraw payment should fail");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    assert!(err.1.contains("insufficient"));
+    let events = crate::legal::audit::test_events_snapshot();
+    assert!(
+        events
+            .iter()
+            .any(|event| event.event_type == "trust_withdrawal_rejected")
+    );
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn trust_statement_import_compute_and_signoff_round_trip() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+
+    let Json(account) = trust_account_put_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(UpdateTrustAccountRequest {
+            name: "Primary IOLTA".to_string(),
+            bank_name: Some("Bar Bank".to_string()),
+            account_number_last4: Some("1234".to_string()),
+        }),
+    )
+    .await
+    .expect("configure trust account");
+    assert_eq!(account.name, "Primary IOLTA");
+
+    let _ = matter_trust_deposit_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(TrustDepositRequest {
+            amount: "500.00".to_string(),
+            recorded_by: "Lead".to_string(),
+            description: Some("Initial retainer".to_string()),
+            reference_number: Some("DEP-001".to_string()),
+        }),
+    )
+    .await
+    .expect("seed trust deposit");
+
+    let (_status, Json(imported)) = trust_statements_import_handler(
+        State(Arc::clone(&state)),
+        Json(ImportTrustStatementRequest {
+            imported_by: "Lead".to_string(),
+            statement_date: Some("2026-04-30".to_string()),
+            csv: "date,description,debit,credit,balance,reference\n2026-04-12,Initial retainer,,500.00,500.00,DEP-001\n"
+                .to_string(),
+        }),
+    )
+    .await
+    .expect("statement import should succeed");
+    assert_eq!(imported.statement.row_count, 1);
+
+    let (_status, Json(reconciliation)) = trust_reconciliations_compute_handler(
+        State(Arc::clone(&state)),
+        Json(ComputeTrustReconciliationRequest {
+            statement_import_id: imported.statement.id.clone(),
+        }),
+    )
+    .await
+    .expect("reconciliation compute should succeed");
+    assert_eq!(reconciliation.status, "draft");
+    assert_eq!(
+        reconciliation
+            .difference
+            .parse::<rust_decimal::Decimal>()
+            .expect("parse reconciliation difference"),
+        rust_decimal::Decimal::ZERO
+    );
+
+    let Json(signed_off) = trust_reconciliations_signoff_handler(
+        State(Arc::clone(&state)),
+        Path(reconciliation.id.clone()),
+        Json(SignoffTrustReconciliationRequest {
+            signed_off_by: "Lead".to_string(),
+        }),
+    )
+    .await
+    .expect("reconciliation signoff should succeed");
+    assert_eq!(signed_off.status, "signed_off");
+    assert_eq!(signed_off.signed_off_by.as_deref(), Some("Lead"));
+
+    let Json(ledger) = matter_trust_ledger_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("trust ledger should load");
+    assert_eq!(
+        ledger
+            .latest_reconciliation
+            .as_ref()
+            .expect("latest reconciliation should exist")
+            .id,
+        reconciliation.id
+    );
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn invoices_payment_rejects_draft_invoice_status() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
+
+    store
+        .create_expense_entry(
+            &state.user_id,
+            "demo",
+            &crate::db::CreateExpenseEntryParams {
+                submitted_by: "Lead".to_string(),
+                description: "Draft-only charge".to_string(),
+                amount: rust_decimal::Decimal::new(10000, 2),
+                category: crate::db::ExpenseCategory::Other,
+                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 6).expect("valid date"),
+                receipt_path: None,
+                billable: true,
+            },
+        )
+        .await
+        .expect("seed expense entry");
+
+    let (_status, Json(created)) = invoices_save_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(DraftInvoiceRequest {
+            matter_id: "demo".to_string(),
+            invoice_number: "INV-DRAFT-100".to_string(),
+            due_date: Some("2026-06-06".to_string()),
+            notes: None,
+        }),
+    )
+    .await
+    .expect("save draft should succeed");
+
+    let err = invoices_payment_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(created.invoice.id.clone()),
+        Json(RecordInvoicePaymentRequest {
+            amount: "25.00".to_string(),
+            recorded_by: "Lead".to_string(),
+            draw_from_trust: false,
+            description: Some("Should fail on draft".to_string()),
+        }),
+    )
+    .await
+    .expect_err("payment on draft invoice should fail");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    assert!(err.1.contains("status 'draft'"));
+
+    let invoice_after = store
+        .get_invoice(
+            &state.user_id,
+            Uuid::parse_str(&created.invoice.id).expect("invoice uuid"),
+        )
+        .await
+        .expect("load invoice")
+        .expect("invoice exists");
+    assert_eq!(invoice_after.status, crate::db::InvoiceStatus::Draft);
+    assert_eq!(invoice_after.paid_amount, rust_decimal::Decimal::ZERO);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn invoices_payment_rejects_amount_above_remaining_balance() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
+
+    store
+        .create_expense_entry(
+            &state.user_id,
+            "demo",
+            &crate::db::CreateExpenseEntryParams {
+                submitted_by: "Lead".to_string(),
+                description: "Large service".to_string(),
+                amount: rust_decimal::Decimal::new(10000, 2),
+                category: crate::db::ExpenseCategory::Other,
+                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 8).expect("valid date"),
+                receipt_path: None,
+                billable: true,
+            },
+        )
+        .await
+        .expect("seed expense entry");
+
+    let (_status, Json(created)) = invoices_save_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(DraftInvoiceRequest {
+            matter_id: "demo".to_string(),
+            invoice_number: "INV-REM-100".to_string(),
+            due_date: Some("2026-06-08".to_string()),
+            notes: None,
+        }),
+    )
+    .await
+    .expect("save draft should succeed");
+    let invoice_id = created.invoice.id.clone();
+    let _ = invoices_finalize_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice_id.clone()),
+    )
+    .await
+    .expect("finalize should succeed");
+
+    let _ = invoices_payment_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice_id.clone()),
+        Json(RecordInvoicePaymentRequest {
+            amount: "60.00".to_string(),
+            recorded_by: "Lead".to_string(),
+            draw_from_trust: false,
+            description: Some("Initial partial payment".to_string()),
+        }),
+    )
+    .await
+    .expect("first payment should succeed");
+
+    let err = invoices_payment_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice_id.clone()),
+        Json(RecordInvoicePaymentRequest {
+            amount: "50.00".to_string(),
+            recorded_by: "Lead".to_string(),
+            draw_from_trust: false,
+            description: Some("Should exceed remaining".to_string()),
+        }),
+    )
+    .await
+    .expect_err("payment above remaining should fail");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    assert!(err.1.contains("exceeds remaining balance"));
+
+    let invoice_after = store
+        .get_invoice(
+            &state.user_id,
+            Uuid::parse_str(&invoice_id).expect("invoice uuid"),
+        )
+        .await
+        .expect("load invoice")
+        .expect("invoice exists");
+    assert_eq!(
+        invoice_after.paid_amount,
+        rust_decimal::Decimal::new(6000, 2)
+    );
+    assert_eq!(invoice_after.status, crate::db::InvoiceStatus::Sent);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn invoices_payment_exact_remaining_marks_invoice_paid() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
+
+    store
+        .create_expense_entry(
+            &state.user_id,
+            "demo",
+            &crate::db::CreateExpenseEntryParams {
+                submitted_by: "Lead".to_string(),
+                description: "Precision test service".to_string(),
+                amount: rust_decimal::Decimal::new(3000, 2),
+                category: crate::db::ExpenseCategory::Other,
+                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 10).expect("valid date"),
+                receipt_path: None,
+                billable: true,
+            },
+        )
+        .await
+        .expect("seed expense entry");
+
+    let (_status, Json(created)) = invoices_save_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(DraftInvoiceRequest {
+            matter_id: "demo".to_string(),
+            invoice_number: "INV-EXACT-PAID".to_string(),
+            due_date: Some("2026-06-10".to_string()),
+            notes: None,
+        }),
+    )
+    .await
+    .expect("save draft should succeed");
+    let invoice_id = created.invoice.id.clone();
+    let _ = invoices_finalize_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice_id.clone()),
+    )
+    .await
+    .expect("finalize should succeed");
+
+    let _ = invoices_payment_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice_id.clone()),
+        Json(RecordInvoicePaymentRequest {
+            amount: "29.99".to_string(),
+            recorded_by: "Lead".to_string(),
+            draw_from_trust: false,
+            description: Some("Primary payment".to_string()),
+        }),
+    )
+    .await
+    .expect("first payment should succeed");
+
+    let _ = invoices_payment_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice_id.clone()),
+        Json(RecordInvoicePaymentRequest {
+            amount: "0.01".to_string(),
+            recorded_by: "Lead".to_string(),
+            draw_from_trust: false,
+            description: Some("Exact remaining payment".to_string()),
+        }),
+    )
+    .await
+    .expect("second payment should succeed");
+
+    let invoice_after = store
+        .get_invoice(
+            &state.user_id,
+            Uuid::parse_str(&invoice_id).expect("invoice uuid"),
+        )
+        .await
+        .expect("load invoice")
+        .expect("invoice exists");
+    assert_eq!(
+        invoice_after.paid_amount,
+        rust_decimal::Decimal::new(3000, 2)
+    );
+    assert_eq!(invoice_after.status, crate::db::InvoiceStatus::Paid);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn invoices_finalize_rejects_line_items_already_billed_elsewhere() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
+
+    store
+        .create_expense_entry(
+            &state.user_id,
+            "demo",
+            &crate::db::CreateExpenseEntryParams {
+                submitted_by: "Lead".to_string(),
+                description: "Shared charge".to_string(),
+                amount: rust_decimal::Decimal::new(10000, 2),
+                category: crate::db::ExpenseCategory::Other,
+                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 9).expect("valid date"),
+                receipt_path: None,
+                billable: true,
+            },
+        )
+        .await
+        .expect("seed expense entry");
+
+    let (_, Json(first)) = invoices_save_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(DraftInvoiceRequest {
+            matter_id: "demo".to_string(),
+            invoice_number: "INV-SHARED-1".to_string(),
+            due_date: Some("2026-06-09".to_string()),
+            notes: None,
+        }),
+    )
+    .await
+    .expect("save first draft");
+    let (_, Json(second)) = invoices_save_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(DraftInvoiceRequest {
+            matter_id: "demo".to_string(),
+            invoice_number: "INV-SHARED-2".to_string(),
+            due_date: Some("2026-06-10".to_string()),
+            notes: None,
+        }),
+    )
+    .await
+    .expect("save second draft");
+    assert_eq!(first.line_items.len(), 1);
+    assert_eq!(second.line_items.len(), 1);
+
+    let _ = invoices_finalize_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(first.invoice.id.clone()),
+    )
+    .await
+    .expect("first finalize should succeed");
+
+    let err = invoices_finalize_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(second.invoice.id.clone()),
+    )
+    .await
+    .expect_err("second finalize should be blocked");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    assert!(err.1.contains("already billed"));
+
+    let second_invoice = store
+        .get_invoice(
+            &state.user_id,
+            Uuid::parse_str(&second.invoice.id).expect("invoice uuid"),
+        )
+        .await
+        .expect("load second invoice")
+        .expect("second invoice exists");
+    assert_eq!(second_invoice.status, crate::db::InvoiceStatus::Draft);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn invoices_payment_concurrent_requests_allow_single_winner() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
+
+    store
+        .create_expense_entry(
+            &state.user_id,
+            "demo",
+            &crate::db::CreateExpenseEntryParams {
+                submitted_by: "Lead".to_string(),
+                description: "Concurrent payable".to_string(),
+                amount: rust_decimal::Decimal::new(10000, 2),
+                category: crate::db::ExpenseCategory::Other,
+                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 10).expect("valid date"),
+                receipt_path: None,
+                billable: true,
+            },
+        )
+        .await
+        .expect("seed expense entry");
+
+    let (_, Json(created)) = invoices_save_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(DraftInvoiceRequest {
+            matter_id: "demo".to_string(),
+            invoice_number: "INV-CONCURRENT-1".to_string(),
+            due_date: Some("2026-06-10".to_string()),
+            notes: None,
+        }),
+    )
+    .await
+    .expect("save draft");
+    let invoice_id = created.invoice.id.clone();
+    let _ = invoices_finalize_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice_id.clone()),
+    )
+    .await
+    .expect("finalize should succeed");
+
+    let barrier = Arc::new(tokio::sync::Barrier::new(3));
+    let barrier_a = Arc::clone(&barrier);
+    let state_a = Arc::clone(&state);
+    let invoice_a = invoice_id.clone();
+    let task_a = tokio::spawn(async move {
+        barrier_a.wait().await;
+        invoices_payment_handler(
+            State(state_a),
+            owner_principal(),
+            Path(invoice_a),
+            Json(RecordInvoicePaymentRequest {
+                amount: "80.00".to_string(),
+                recorded_by: "Lead A".to_string(),
+                draw_from_trust: false,
+                description: Some("Concurrent payment A".to_string()),
+            }),
+        )
+        .await
+    });
+
+    let barrier_b = Arc::clone(&barrier);
+    let state_b = Arc::clone(&state);
+    let invoice_b = invoice_id.clone();
+    let task_b = tokio::spawn(async move {
+        barrier_b.wait().await;
+        invoices_payment_handler(
+            State(state_b),
+            owner_principal(),
+            Path(invoice_b),
+            Json(RecordInvoicePaymentRequest {
+                amount: "80.00".to_string(),
+                recorded_by: "Lead B".to_string(),
+                draw_from_trust: false,
+                description: Some("Concurrent payment B".to_string()),
+            }),
+        )
+        .await
+    });
+
+    barrier.wait().await;
+    let result_a = task_a.await.expect("task A joined");
+    let result_b = task_b.await.expect("task B joined");
+    let success_count = usize::from(result_a.is_ok()) + usize::from(result_b.is_ok());
+    assert_eq!(
+        success_count, 1,
+        "exactly one concurrent payment should succeed"
+    );
+
+    let invoice_after = store
+        .get_invoice(
+            &state.user_id,
+            Uuid::parse_str(&invoice_id).expect("invoice uuid"),
+        )
+        .await
+        .expect("load invoice")
+        .expect("invoice exists");
+    assert_eq!(
+        invoice_after.paid_amount,
+        rust_decimal::Decimal::new(8000, 2),
+        "paid_amount should reflect only one successful concurrent payment"
+    );
+    assert_eq!(invoice_after.status, crate::db::InvoiceStatus::Sent);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn invoices_payment_concurrent_trust_draw_creates_single_debit_entry() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
+
+    store
+        .create_expense_entry(
+            &state.user_id,
+            "demo",
+            &crate::db::CreateExpenseEntryParams {
+                submitted_by: "Lead".to_string(),
+                description: "Concurrent trust payable".to_string(),
+                amount: rust_decimal::Decimal::new(10000, 2),
+                category: crate::db::ExpenseCategory::Other,
+                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 11).expect("valid date"),
+                receipt_path: None,
+                billable: true,
+            },
+        )
+        .await
+        .expect("seed expense entry");
+
+    let (_, Json(created)) = invoices_save_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(DraftInvoiceRequest {
+            matter_id: "demo".to_string(),
+            invoice_number: "INV-CONCURRENT-TRUST-1".to_string(),
+            due_date: Some("2026-06-11".to_string()),
+            notes: None,
+        }),
+    )
+    .await
+    .expect("save draft");
+    let invoice_id = created.invoice.id.clone();
+    let _ = invoices_finalize_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice_id.clone()),
+    )
+    .await
+    .expect("finalize should succeed");
+
+    let _ = matter_trust_deposit_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(TrustDepositRequest {
+            amount: "100.00".to_string(),
+            recorded_by: "Lead".to_string(),
+            description: Some("Concurrent trust funding".to_string()),
+            reference_number: None,
+        }),
+    )
+    .await
+    .expect("trust deposit should succeed");
+
+    let barrier = Arc::new(tokio::sync::Barrier::new(3));
+    let barrier_a = Arc::clone(&barrier);
+    let state_a = Arc::clone(&state);
+    let invoice_a = invoice_id.clone();
+    let task_a = tokio::spawn(async move {
+        barrier_a.wait().await;
+        invoices_payment_handler(
+            State(state_a),
+            owner_principal(),
+            Path(invoice_a),
+            Json(RecordInvoicePaymentRequest {
+                amount: "60.00".to_string(),
+                recorded_by: "Lead A".to_string(),
+                draw_from_trust: true,
+                description: Some("Concurrent trust payment A".to_string()),
+            }),
+        )
+        .await
+    });
+
+    let barrier_b = Arc::clone(&barrier);
+    let state_b = Arc::clone(&state);
+    let invoice_b = invoice_id.clone();
+    let task_b = tokio::spawn(async move {
+        barrier_b.wait().await;
+        invoices_payment_handler(
+            State(state_b),
+            owner_principal(),
+            Path(invoice_b),
+            Json(RecordInvoicePaymentRequest {
+                amount: "60.00".to_string(),
+                recorded_by: "Lead B".to_string(),
+                draw_from_trust: true,
+                description: Some("Concurrent trust payment B".to_string()),
+            }),
+        )
+        .await
+    });
+
+    barrier.wait().await;
+    let result_a = task_a.await.expect("task A joined");
+    let result_b = task_b.await.expect("task B joined");
+    let success_count = usize::from(result_a.is_ok()) + usize::from(result_b.is_ok());
+    assert_eq!(
+        success_count, 1,
+        "exactly one concurrent trust draw should succeed"
+    );
+
+    let invoice_after = store
+        .get_invoice(
+            &state.user_id,
+            Uuid::parse_str(&invoice_id).expect("invoice uuid"),
+        )
+        .await
+        .expect("load invoice")
+        .expect("invoice exists");
+    assert_eq!(
+        invoice_after.paid_amount,
+        rust_decimal::Decimal::new(6000, 2)
+    );
+
+    let ledger = store
+        .list_trust_ledger_entries(&state.user_id, "demo")
+        .await
+        .expect("load trust ledger");
+    let invoice_payment_count = ledger
+        .iter()
+        .filter(|entry| entry.entry_type == crate::db::TrustLedgerEntryType::InvoicePayment)
+        .count();
+    assert_eq!(invoice_payment_count, 1);
+    let balance = store
+        .current_trust_balance(&state.user_id, "demo")
+        .await
+        .expect("current trust balance");
+    assert_eq!(balance, rust_decimal::Decimal::new(4000, 2));
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn invoices_void_rejects_paid_invoice_status() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
+
+    store
+        .create_expense_entry(
+            &state.user_id,
+            "demo",
+            &crate::db::CreateExpenseEntryParams {
+                submitted_by: "Lead".to_string(),
+                description: "Payable service".to_string(),
+                amount: rust_decimal::Decimal::new(10000, 2),
+                category: crate::db::ExpenseCategory::Other,
+                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 7).expect("valid date"),
+                receipt_path: None,
+                billable: true,
+            },
+        )
+        .await
+        .expect("seed expense entry");
+
+    let (_status, Json(created)) = invoices_save_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(DraftInvoiceRequest {
+            matter_id: "demo".to_string(),
+            invoice_number: "INV-PAID-100".to_string(),
+            due_date: Some("2026-06-07".to_string()),
+            notes: None,
+        }),
+    )
+    .await
+    .expect("save draft should succeed");
+
+    let invoice_id = created.invoice.id.clone();
+    let _ = invoices_finalize_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice_id.clone()),
+    )
+    .await
+    .expect("finalize should succeed");
+
+    let _ = invoices_payment_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice_id.clone()),
+        Json(RecordInvoicePaymentRequest {
+            amount: "100.00".to_string(),
+            recorded_by: "Lead".to_string(),
+            draw_from_trust: false,
+            description: Some("Mark paid".to_string()),
+        }),
+    )
+    .await
+    .expect("payment should succeed");
+
+    let err = invoices_void_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice_id.clone()),
+    )
+    .await
+    .expect_err("void on paid invoice should fail");
+    assert_eq!(err.0, StatusCode::CONFLICT);
+    assert!(err.1.contains("status 'paid'"));
+
+    let invoice_after = store
+        .get_invoice(
+            &state.user_id,
+            Uuid::parse_str(&invoice_id).expect("invoice uuid"),
+        )
+        .await
+        .expect("load invoice")
+        .expect("invoice exists");
+    assert_eq!(invoice_after.status, crate::db::InvoiceStatus::Paid);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn trust_deposits_concurrently_update_balance_atomically() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist").clone();
+    let user_id = state.user_id.clone();
+    let barrier = Arc::new(tokio::sync::Barrier::new(3));
+
+    let barrier_a = Arc::clone(&barrier);
+    let store_a = Arc::clone(&store);
+    let user_a = user_id.clone();
+    let task_a = tokio::spawn(async move {
+        barrier_a.wait().await;
+        crate::legal::billing::record_trust_deposit(
+            store_a.as_ref(),
+            &user_a,
+            "demo",
+            rust_decimal::Decimal::new(5000, 2),
+            "Lead A",
+            "Concurrent deposit A",
+            None,
+        )
+        .await
+    });
+
+    let barrier_b = Arc::clone(&barrier);
+    let store_b = Arc::clone(&store);
+    let user_b = user_id.clone();
+    let task_b = tokio::spawn(async move {
+        barrier_b.wait().await;
+        crate::legal::billing::record_trust_deposit(
+            store_b.as_ref(),
+            &user_b,
+            "demo",
+            rust_decimal::Decimal::new(5000, 2),
+            "Lead B",
+            "Concurrent deposit B",
+            None,
+        )
+        .await
+    });
+
+    barrier.wait().await;
+    let entry_a = task_a
+        .await
+        .expect("task A should join")
+        .expect("deposit A should succeed");
+    let entry_b = task_b
+        .await
+        .expect("task B should join")
+        .expect("deposit B should succeed");
+
+    let mut balances = vec![entry_a.balance_after, entry_b.balance_after];
+    balances.sort();
+    assert_eq!(
+        balances,
+        vec![
+            rust_decimal::Decimal::new(5000, 2),
+            rust_decimal::Decimal::new(10000, 2)
+        ]
+    );
+
+    let balance = store
+        .current_trust_balance(&state.user_id, "demo")
+        .await
+        .expect("read balance");
+    assert_eq!(balance, rust_decimal::Decimal::new(10000, 2));
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_time_create_rejects_non_positive_hours() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+
+    let result = matter_time_create_handler(
+        State(state),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateTimeEntryRequest {
+            timekeeper: "Paralegal".to_string(),
+            description: "Prepare draft".to_string(),
+            hours: "0".to_string(),
+            hourly_rate: Some("200".to_string()),
+            task_code: None,
+            activity_code: None,
+            entry_date: "2026-04-10".to_string(),
+            billable: Some(true),
+            block_billing_flag: None,
+            block_billing_reason: None,
+        }),
+    )
+    .await;
+
+    let err = result.expect_err("zero-hour time entry should be rejected");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    assert!(err.1.contains("'hours' must be greater than 0"));
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_expense_create_rejects_non_positive_amount() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+
+    let result = matter_expenses_create_handler(
+        State(state),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateExpenseEntryRequest {
+            submitted_by: "Associate".to_string(),
+            description: "Filing fee".to_string(),
+            amount: "0".to_string(),
+            category: "filing_fee".to_string(),
+            entry_date: "2026-04-10".to_string(),
+            receipt_path: None,
+            billable: Some(true),
+        }),
+    )
+    .await;
+
+    let err = result.expect_err("zero-amount expense entry should be rejected");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    assert!(err.1.contains("'amount' must be greater than 0"));
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_time_delete_rejects_billed_entry() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
+
+    let billed_entry = store
+        .create_time_entry(
+            &state.user_id,
+            "demo",
+            &crate::db::CreateTimeEntryParams {
+                timekeeper: "Lead".to_string(),
+                description: "Billed work".to_string(),
+                hours: rust_decimal::Decimal::new(150, 2),
+                hourly_rate: Some(rust_decimal::Decimal::new(30000, 2)),
+                task_code: None,
+                activity_code: None,
+                resolved_rate: None,
+                rate_source: None,
+                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 4, 9).expect("valid date"),
+                billable: true,
+                block_billing_flag: false,
+                block_billing_reason: None,
+            },
+        )
+        .await
+        .expect("create billed seed entry");
+    let unbilled_entry = store
+        .create_time_entry(
+            &state.user_id,
+            "demo",
+            &crate::db::CreateTimeEntryParams {
+                timekeeper: "Lead".to_string(),
+                description: "Unbilled work".to_string(),
+                hours: rust_decimal::Decimal::new(50, 2),
+                hourly_rate: Some(rust_decimal::Decimal::new(30000, 2)),
+                task_code: None,
+                activity_code: None,
+                resolved_rate: None,
+                rate_source: None,
+                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 4, 10).expect("valid date"),
+                billable: true,
+                block_billing_flag: false,
+                block_billing_reason: None,
+            },
+        )
+        .await
+        .expect("create unbilled seed entry");
+
+    let marked = store
+        .mark_time_entries_billed(&state.user_id, &[billed_entry.id], "inv-1001")
+        .await
+        .expect("mark billed entry");
+    assert_eq!(marked, 1);
+
+    let billed_after = store
+        .get_time_entry(&state.user_id, "demo", billed_entry.id)
+        .await
+        .expect("load billed entry")
+        .expect("billed entry should exist");
+    let unbilled_after = store
+        .get_time_entry(&state.user_id, "demo", unbilled_entry.id)
+        .await
+        .expect("load unbilled entry")
+        .expect("unbilled entry should exist");
+    assert_eq!(billed_after.billed_invoice_id.as_deref(), Some("inv-1001"));
+    assert!(unbilled_after.billed_invoice_id.is_none());
+
+    let billed_delete = matter_time_delete_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(("demo".to_string(), billed_entry.id.to_string())),
+    )
+    .await;
+    let err = billed_delete.expect_err("billed entry should not be deletable");
+    assert_eq!(err.0, StatusCode::CONFLICT);
+    assert!(err.1.contains("billed"));
+
+    let unbilled_delete = matter_time_delete_handler(
+        State(state),
+        owner_principal(),
+        Path(("demo".to_string(), unbilled_entry.id.to_string())),
+    )
+    .await
+    .expect("unbilled entry should be deletable");
+    assert_eq!(unbilled_delete, StatusCode::NO_CONTENT);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_time_summary_aggregates_hours_and_expenses() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
+
+    let time_one = store
+        .create_time_entry(
+            &state.user_id,
+            "demo",
+            &crate::db::CreateTimeEntryParams {
+                timekeeper: "Lead".to_string(),
+                description: "Billable review".to_string(),
+                hours: rust_decimal::Decimal::new(150, 2),
+                hourly_rate: Some(rust_decimal::Decimal::new(35000, 2)),
+                task_code: None,
+                activity_code: None,
+                resolved_rate: None,
+                rate_source: None,
+                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 4, 11).expect("valid date"),
+                billable: true,
+                block_billing_flag: false,
+                block_billing_reason: None,
+            },
+        )
+        .await
+        .expect("create first time entry");
+    store
+        .create_time_entry(
+            &state.user_id,
+            "demo",
+            &crate::db::CreateTimeEntryParams {
+                timekeeper: "Paralegal".to_string(),
+                description: "Internal prep".to_string(),
+                hours: rust_decimal::Decimal::new(50, 2),
+                hourly_rate: None,
+                task_code: None,
+                activity_code: None,
+                resolved_rate: None,
+                rate_source: None,
+                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 4, 11).expect("valid date"),
+                billable: false,
+                block_billing_flag: false,
+                block_billing_reason: None,
+            },
+        )
+        .await
+        .expect("create second time entry");
+    let expense_one = store
+        .create_expense_entry(
+            &state.user_id,
+            "demo",
+            &crate::db::CreateExpenseEntryParams {
+                submitted_by: "Lead".to_string(),
+                description: "Court filing fee".to_string(),
+                amount: rust_decimal::Decimal::new(10000, 2),
+                category: crate::db::ExpenseCategory::FilingFee,
+                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 4, 11).expect("valid date"),
+                receipt_path: None,
+                billable: true,
+            },
+        )
+        .await
+        .expect("create first expense entry");
+    store
+        .create_expense_entry(
+            &state.user_id,
+            "demo",
+            &crate::db::CreateExpenseEntryParams {
+                submitted_by: "Lead".to_string(),
+                description: "Internal courier".to_string(),
+                amount: rust_decimal::Decimal::new(4000, 2),
+                category: crate::db::ExpenseCategory::Other,
+                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 4, 11).expect("valid date"),
+                receipt_path: None,
+                billable: false,
+            },
+        )
+        .await
+        .expect("create second expense entry");
+    store
+        .mark_time_entries_billed(&state.user_id, &[time_one.id], "inv-2001")
+        .await
+        .expect("mark one time entry billed");
+    store
+        .mark_expense_entries_billed(&state.user_id, &[expense_one.id], "inv-2001")
+        .await
+        .expect("mark one expense entry billed");
+
+    let Json(summary) =
+        matter_time_summary_handler(State(state), owner_principal(), Path("demo".to_string()))
+            .await
+            .expect("summary handler should succeed");
+
+    let total_hours = summary
+        .total_hours
+        .parse::<rust_decimal::Decimal>()
+        .expect("parse total hours");
+    let billable_hours = summary
+        .billable_hours
+        .parse::<rust_decimal::Decimal>()
+        .expect("parse billable hours");
+    let unbilled_hours = summary
+        .unbilled_hours
+        .parse::<rust_decimal::Decimal>()
+        .expect("parse unbilled hours");
+    let total_expenses = summary
+        .total_expenses
+        .parse::<rust_decimal::Decimal>()
+        .expect("parse total expenses");
+    let billable_expenses = summary
+        .billable_expenses
+        .parse::<rust_decimal::Decimal>()
+        .expect("parse billable expenses");
+    let unbilled_expenses = summary
+        .unbilled_expenses
+        .parse::<rust_decimal::Decimal>()
+        .expect("parse unbilled expenses");
+
+    assert_eq!(total_hours, rust_decimal::Decimal::new(200, 2));
+    assert_eq!(billable_hours, rust_decimal::Decimal::new(150, 2));
+    assert_eq!(unbilled_hours, rust_decimal::Decimal::new(50, 2));
+    assert_eq!(total_expenses, rust_decimal::Decimal::new(14000, 2));
+    assert_eq!(billable_expenses, rust_decimal::Decimal::new(10000, 2));
+    assert_eq!(unbilled_expenses, rust_decimal::Decimal::new(4000, 2));
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn billing_rates_apply_matter_override_and_export_ledes() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+
+    let (_status, Json(default_rate)) = billing_rates_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(CreateBillingRateScheduleRequest {
+            matter_id: None,
+            timekeeper: "Lead".to_string(),
+            rate: "425.00".to_string(),
+            effective_start: "2026-01-01".to_string(),
+            effective_end: None,
+        }),
+    )
+    .await
+    .expect("create timekeeper default rate");
+    assert_eq!(default_rate.rate, "425.00");
+
+    let (_status, Json(matter_rate)) = billing_rates_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(CreateBillingRateScheduleRequest {
+            matter_id: Some("demo".to_string()),
+            timekeeper: "Lead".to_string(),
+            rate: "375.00".to_string(),
+            effective_start: "2026-01-01".to_string(),
+            effective_end: None,
+        }),
+    )
+    .await
+    .expect("create matter override rate");
+    assert_eq!(matter_rate.matter_id.as_deref(), Some("demo"));
+
+    let (_status, Json(entry)) = matter_time_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateTimeEntryRequest {
+            timekeeper: "Lead".to_string(),
+            description: "Draft motion for summary judgment".to_string(),
+            hours: "1.50".to_string(),
+            hourly_rate: None,
+            task_code: Some("b110".to_string()),
+            activity_code: Some("a101".to_string()),
+            entry_date: "2026-04-10".to_string(),
+            billable: Some(true),
+            block_billing_flag: None,
+            block_billing_reason: None,
+        }),
+    )
+    .await
+    .expect("create rate-managed time entry");
+    assert_eq!(entry.rate_source.as_deref(), Some("matter_override"));
+    assert_eq!(
+        entry
+            .resolved_rate
+            .as_deref()
+            .expect("resolved rate should exist")
+            .parse::<rust_decimal::Decimal>()
+            .expect("parse resolved rate"),
+        rust_decimal::Decimal::new(37500, 2)
+    );
+
+    let (_status, Json(invoice)) = invoices_save_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(DraftInvoiceRequest {
+            matter_id: "demo".to_string(),
+            invoice_number: "INV-LEDES-100".to_string(),
+            due_date: Some("2026-05-10".to_string()),
+            notes: None,
+        }),
+    )
+    .await
+    .expect("draft invoice should succeed");
+    let _ = invoices_finalize_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice.invoice.id.clone()),
+    )
+    .await
+    .expect("finalize invoice should succeed");
+
+    let (status, Json(ledes)) = invoice_ledes_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice.invoice.id.clone()),
+        Query(InvoiceLedesQuery {
+            format: Some("98b".to_string()),
+        }),
+    )
+    .await
+    .expect("LEDES export should succeed");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ledes.format, "98b");
+    let content = ledes.content.expect("LEDES content should exist");
+    assert!(content.starts_with("LEDES1998B[]\n"));
+    assert!(content.contains("|B110||A101|"));
+    assert!(content.contains("|375.00|Lead|OT|"));
+    assert!(ledes.errors.is_empty());
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn billing_rates_enforce_rbac_and_overlap_validation() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    seed_valid_matter(workspace.as_ref(), "other").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync demo matter row");
+    ensure_matter_db_row_from_workspace(state.as_ref(), "other")
+        .await
+        .expect("sync other matter row");
+
+    let staff_principal = principal_with_role("staff-user", UserRole::Staff);
+    let Json(list) = billing_rates_list_handler(State(Arc::clone(&state)), staff_principal.clone())
+        .await
+        .expect("authenticated staff should list rates");
+    assert!(list.schedules.is_empty());
+
+    let create_forbidden = billing_rates_create_handler(
+        State(Arc::clone(&state)),
+        staff_principal.clone(),
+        Json(CreateBillingRateScheduleRequest {
+            matter_id: None,
+            timekeeper: "Lead".to_string(),
+            rate: "425.00".to_string(),
+            effective_start: "2026-01-01".to_string(),
+            effective_end: Some("2026-01-31".to_string()),
+        }),
+    )
+    .await
+    .expect_err("non-admin create should be forbidden");
+    assert_eq!(create_forbidden.0, StatusCode::FORBIDDEN);
+
+    let (_status, Json(january_rate)) = billing_rates_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(CreateBillingRateScheduleRequest {
+            matter_id: None,
+            timekeeper: "Lead".to_string(),
+            rate: "425.00".to_string(),
+            effective_start: "2026-01-01".to_string(),
+            effective_end: Some("2026-01-31".to_string()),
+        }),
+    )
+    .await
+    .expect("admin should create initial schedule");
+
+    let invalid_range = billing_rates_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(CreateBillingRateScheduleRequest {
+            matter_id: None,
+            timekeeper: "Lead".to_string(),
+            rate: "425.00".to_string(),
+            effective_start: "2026-03-01".to_string(),
+            effective_end: Some("2026-02-28".to_string()),
+        }),
+    )
+    .await
+    .expect_err("invalid date range should be rejected");
+    assert_eq!(invalid_range.0, StatusCode::BAD_REQUEST);
+
+    let overlap = billing_rates_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(CreateBillingRateScheduleRequest {
+            matter_id: None,
+            timekeeper: "Lead".to_string(),
+            rate: "430.00".to_string(),
+            effective_start: "2026-01-31".to_string(),
+            effective_end: Some("2026-02-15".to_string()),
+        }),
+    )
+    .await
+    .expect_err("inclusive date overlap should be rejected");
+    assert_eq!(overlap.0, StatusCode::CONFLICT);
+
+    let (_status, Json(february_rate)) = billing_rates_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(CreateBillingRateScheduleRequest {
+            matter_id: None,
+            timekeeper: "Lead".to_string(),
+            rate: "430.00".to_string(),
+            effective_start: "2026-02-01".to_string(),
+            effective_end: None,
+        }),
+    )
+    .await
+    .expect("adjacent non-overlapping range should succeed");
+
+    let (_status, Json(other_matter_rate)) = billing_rates_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(CreateBillingRateScheduleRequest {
+            matter_id: Some("other".to_string()),
+            timekeeper: "Lead".to_string(),
+            rate: "390.00".to_string(),
+            effective_start: "2026-01-15".to_string(),
+            effective_end: None,
+        }),
+    )
+    .await
+    .expect("same timekeeper on different matter scope should succeed");
+    assert_eq!(other_matter_rate.matter_id.as_deref(), Some("other"));
+
+    let (_status, Json(other_timekeeper_rate)) = billing_rates_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(CreateBillingRateScheduleRequest {
+            matter_id: None,
+            timekeeper: "Associate".to_string(),
+            rate: "275.00".to_string(),
+            effective_start: "2026-01-15".to_string(),
+            effective_end: None,
+        }),
+    )
+    .await
+    .expect("different timekeeper should not conflict");
+    assert_eq!(other_timekeeper_rate.timekeeper, "Associate");
+
+    let patch_forbidden = billing_rates_patch_handler(
+        State(Arc::clone(&state)),
+        staff_principal,
+        Path(february_rate.id.clone()),
+        Json(UpdateBillingRateScheduleRequest {
+            matter_id: None,
+            timekeeper: None,
+            rate: Some("440.00".to_string()),
+            effective_start: None,
+            effective_end: None,
+        }),
+    )
+    .await
+    .expect_err("non-admin patch should be forbidden");
+    assert_eq!(patch_forbidden.0, StatusCode::FORBIDDEN);
+
+    let patch_overlap = billing_rates_patch_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(february_rate.id.clone()),
+        Json(UpdateBillingRateScheduleRequest {
+            matter_id: None,
+            timekeeper: None,
+            rate: None,
+            effective_start: Some("2026-01-15".to_string()),
+            effective_end: None,
+        }),
+    )
+    .await
+    .expect_err("patch creating overlap should be rejected");
+    assert_eq!(patch_overlap.0, StatusCode::CONFLICT);
+
+    let Json(updated) = billing_rates_patch_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(january_rate.id.clone()),
+        Json(UpdateBillingRateScheduleRequest {
+            matter_id: None,
+            timekeeper: None,
+            rate: Some("410.00".to_string()),
+            effective_start: None,
+            effective_end: None,
+        }),
+    )
+    .await
+    .expect("admin patch should succeed");
+    assert_eq!(updated.rate, "410.00");
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn invoice_draft_reports_rate_resolution_and_fallbacks() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+
+    let (_status, Json(_default_rate)) = billing_rates_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(CreateBillingRateScheduleRequest {
+            matter_id: None,
+            timekeeper: "Lead".to_string(),
+            rate: "425.00".to_string(),
+            effective_start: "2026-01-01".to_string(),
+            effective_end: None,
+        }),
+    )
+    .await
+    .expect("create default rate");
+    let (_status, Json(matter_rate)) = billing_rates_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(CreateBillingRateScheduleRequest {
+            matter_id: Some("demo".to_string()),
+            timekeeper: "Lead".to_string(),
+            rate: "375.00".to_string(),
+            effective_start: "2026-01-01".to_string(),
+            effective_end: None,
+        }),
+    )
+    .await
+    .expect("create matter override rate");
+
+    let _ = matter_time_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateTimeEntryRequest {
+            timekeeper: "Lead".to_string(),
+            description: "Draft complaint".to_string(),
+            hours: "1.00".to_string(),
+            hourly_rate: None,
+            task_code: Some("b110".to_string()),
+            activity_code: Some("a101".to_string()),
+            entry_date: "2026-04-10".to_string(),
+            billable: Some(true),
+            block_billing_flag: None,
+            block_billing_reason: None,
+        }),
+    )
+    .await
+    .expect("create matter-override time entry");
+    let _ = matter_time_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateTimeEntryRequest {
+            timekeeper: "Associate".to_string(),
+            description: "Review production".to_string(),
+            hours: "2.00".to_string(),
+            hourly_rate: Some("250.00".to_string()),
+            task_code: Some("b120".to_string()),
+            activity_code: Some("a102".to_string()),
+            entry_date: "2026-04-10".to_string(),
+            billable: Some(true),
+            block_billing_flag: None,
+            block_billing_reason: None,
+        }),
+    )
+    .await
+    .expect("create manual-rate time entry");
+    let _ = matter_time_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateTimeEntryRequest {
+            timekeeper: "Paralegal".to_string(),
+            description: "Prepare binders".to_string(),
+            hours: "0.50".to_string(),
+            hourly_rate: None,
+            task_code: Some("b130".to_string()),
+            activity_code: Some("a103".to_string()),
+            entry_date: "2026-04-10".to_string(),
+            billable: Some(true),
+            block_billing_flag: None,
+            block_billing_reason: None,
+        }),
+    )
+    .await
+    .expect("create no-rate time entry");
+
+    let Json(draft) = invoices_draft_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(DraftInvoiceRequest {
+            matter_id: "demo".to_string(),
+            invoice_number: "INV-DRAFT-200".to_string(),
+            due_date: Some("2026-05-10".to_string()),
+            notes: None,
+        }),
+    )
+    .await
+    .expect("draft invoice should succeed");
+
+    let matter_item = draft
+        .line_items
+        .iter()
+        .find(|item| item.timekeeper.as_deref() == Some("Lead"))
+        .expect("matter override line item");
+    let matter_resolution = matter_item
+        .rate_resolution
+        .as_ref()
+        .expect("matter override should include rate resolution");
+    assert_eq!(matter_item.rate_source.as_deref(), Some("matter_override"));
+    assert!(!matter_resolution.fallback_applied);
+    assert_eq!(matter_resolution.fallback_reason, None);
+    assert_eq!(
+        matter_resolution
+            .matched_schedule
+            .as_ref()
+            .expect("matched schedule should exist")
+            .id,
+        matter_rate.id
+    );
+    assert_eq!(
+        matter_resolution
+            .matched_schedule
+            .as_ref()
+            .and_then(|schedule| schedule.matter_id.as_deref()),
+        Some("demo")
+    );
+
+    let manual_item = draft
+        .line_items
+        .iter()
+        .find(|item| item.timekeeper.as_deref() == Some("Associate"))
+        .expect("manual fallback line item");
+    let manual_resolution = manual_item
+        .rate_resolution
+        .as_ref()
+        .expect("manual fallback should include rate resolution");
+    assert_eq!(manual_item.rate_source.as_deref(), Some("manual_override"));
+    assert_eq!(manual_item.resolved_rate.as_deref(), Some("250.00"));
+    assert!(manual_resolution.fallback_applied);
+    assert_eq!(
+        manual_resolution.fallback_reason.as_deref(),
+        Some("manual_override")
+    );
+    assert!(manual_resolution.matched_schedule.is_none());
+
+    let no_rate_item = draft
+        .line_items
+        .iter()
+        .find(|item| item.timekeeper.as_deref() == Some("Paralegal"))
+        .expect("no-rate line item");
+    let no_rate_resolution = no_rate_item
+        .rate_resolution
+        .as_ref()
+        .expect("no-rate review should exist");
+    assert_eq!(no_rate_item.rate_source, None);
+    assert_eq!(no_rate_item.resolved_rate, None);
+    assert_eq!(no_rate_item.unit_price, "0");
+    assert_eq!(
+        no_rate_resolution.fallback_reason.as_deref(),
+        Some("no_rate_found")
+    );
+    assert!(no_rate_resolution.fallback_applied);
+    assert!(no_rate_resolution.matched_schedule.is_none());
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn invoice_ledes_returns_structured_validation_errors_and_ignores_expenses() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+
+    let (_status, Json(time_entry)) = matter_time_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateTimeEntryRequest {
+            timekeeper: "Lead".to_string(),
+            description: "Research issues".to_string(),
+            hours: "1.00".to_string(),
+            hourly_rate: Some("300.00".to_string()),
+            task_code: None,
+            activity_code: None,
+            entry_date: "2026-04-12".to_string(),
+            billable: Some(true),
+            block_billing_flag: None,
+            block_billing_reason: None,
+        }),
+    )
+    .await
+    .expect("create time entry");
+    let _ = matter_expenses_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateExpenseEntryRequest {
+            submitted_by: "Lead".to_string(),
+            description: "Filing fee".to_string(),
+            amount: "45.00".to_string(),
+            category: "filing_fee".to_string(),
+            entry_date: "2026-04-12".to_string(),
+            receipt_path: None,
+            billable: Some(true),
+        }),
+    )
+    .await
+    .expect("create expense entry");
+
+    let (_status, Json(invoice)) = invoices_save_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(DraftInvoiceRequest {
+            matter_id: "demo".to_string(),
+            invoice_number: "INV-LEDES-ERR".to_string(),
+            due_date: Some("2026-05-10".to_string()),
+            notes: None,
+        }),
+    )
+    .await
+    .expect("save invoice draft");
+    let _ = invoices_finalize_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice.invoice.id.clone()),
+    )
+    .await
+    .expect("finalize invoice");
+
+    let (status, Json(ledes)) = invoice_ledes_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice.invoice.id.clone()),
+        Query(InvoiceLedesQuery {
+            format: Some("98b".to_string()),
+        }),
+    )
+    .await
+    .expect("LEDES validation should return structured response");
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(ledes.format, "98b");
+    assert!(ledes.content.is_none());
+    assert_eq!(ledes.errors.len(), 1);
+    assert_eq!(
+        ledes.errors[0].time_entry_id.as_deref(),
+        Some(time_entry.id.as_str())
+    );
+    assert_eq!(
+        ledes.errors[0].missing_fields,
+        vec!["task_code".to_string(), "activity_code".to_string()]
+    );
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn finalized_invoice_preserves_rate_snapshot_after_schedule_patch() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+
+    let (_status, Json(default_rate)) = billing_rates_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(CreateBillingRateScheduleRequest {
+            matter_id: None,
+            timekeeper: "Lead".to_string(),
+            rate: "425.00".to_string(),
+            effective_start: "2026-01-01".to_string(),
+            effective_end: None,
+        }),
+    )
+    .await
+    .expect("create default rate");
+
+    let _ = matter_time_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateTimeEntryRequest {
+            timekeeper: "Lead".to_string(),
+            description: "Prepare witness outline".to_string(),
+            hours: "1.00".to_string(),
+            hourly_rate: None,
+            task_code: Some("b110".to_string()),
+            activity_code: Some("a101".to_string()),
+            entry_date: "2026-04-18".to_string(),
+            billable: Some(true),
+            block_billing_flag: None,
+            block_billing_reason: None,
+        }),
+    )
+    .await
+    .expect("create time entry");
+
+    let (_status, Json(invoice)) = invoices_save_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(DraftInvoiceRequest {
+            matter_id: "demo".to_string(),
+            invoice_number: "INV-SNAPSHOT-1".to_string(),
+            due_date: Some("2026-05-18".to_string()),
+            notes: None,
+        }),
+    )
+    .await
+    .expect("save invoice draft");
+    let _ = invoices_finalize_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice.invoice.id.clone()),
+    )
+    .await
+    .expect("finalize invoice");
+
+    let Json(updated_rate) = billing_rates_patch_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(default_rate.id.clone()),
+        Json(UpdateBillingRateScheduleRequest {
+            matter_id: None,
+            timekeeper: None,
+            rate: Some("500.00".to_string()),
+            effective_start: None,
+            effective_end: None,
+        }),
+    )
+    .await
+    .expect("patch billing rate");
+    assert_eq!(updated_rate.rate, "500.00");
+
+    let Json(detail) = invoices_get_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(invoice.invoice.id.clone()),
+    )
+    .await
+    .expect("load finalized invoice detail");
+    let line_item = detail.line_items.first().expect("finalized line item");
+    assert_eq!(line_item.rate_source.as_deref(), Some("timekeeper_default"));
+    assert_eq!(
+        line_item
+            .resolved_rate
+            .as_deref()
+            .expect("resolved rate")
+            .parse::<rust_decimal::Decimal>()
+            .expect("parse resolved rate"),
+        rust_decimal::Decimal::new(42500, 2)
+    );
+    assert_eq!(
+        line_item
+            .unit_price
+            .parse::<rust_decimal::Decimal>()
+            .expect("parse unit price"),
+        rust_decimal::Decimal::new(42500, 2)
+    );
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn jobs_estimation_accuracy_reports_learned_corrections() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    let state = test_gateway_state_with_store_and_workspace(Arc::clone(&db), workspace);
+
+    // Research jobs consistently take 1.5x cost and 2x time.
+    for i in 1..=6 {
+        let job = crate::context::JobContext::new(format!("Research {i}"), "Find precedent");
+        db.save_job(&job).await.expect("save job");
+        let snapshot = db
+            .save_estimation_snapshot(
+                job.job_id,
+                "research",
+                &["memory_search".to_string()],
+                rust_decimal::Decimal::from(i),
+                i * 60,
+                rust_decimal::Decimal::ZERO,
+            )
+            .await
+            .expect("save snapshot");
+        db.update_estimation_actuals(
+            snapshot,
+            rust_decimal::Decimal::from(i) * rust_decimal::Decimal::new(15, 1),
+            i * 120,
+            None,
+        )
+        .await
+        .expect("record actuals");
+    }
+    // A single drafting sample is reported but not yet corrected.
+    let job = crate::context::JobContext::new("Draft", "Draft letter");
+    db.save_job(&job).await.expect("save job");
+    let snapshot = db
+        .save_estimation_snapshot(
+            job.job_id,
+            "drafting",
+            &[],
+            rust_decimal::Decimal::ONE,
+            60,
+            rust_decimal::Decimal::ZERO,
+        )
+        .await
+        .expect("save snapshot");
+    db.update_estimation_actuals(snapshot, rust_decimal::Decimal::ONE, 60, None)
+        .await
+        .expect("record actuals");
+
+    let Json(resp) = jobs_estimation_accuracy_handler(
+        State(Arc::clone(&state)),
+        Query(EstimationAccuracyQuery { category: None }),
+    )
+    .await
+    .expect("accuracy");
+    assert_eq!(resp.min_samples, crate::estimation::MIN_FORECAST_SAMPLES);
+    let categories: Vec<&str> = resp
+        .categories
+        .iter()
+        .map(|c| c.category.as_str())
+        .collect();
+    assert_eq!(categories, vec!["drafting", "research"]);
+    let research = &resp.categories[1];
+    assert_eq!(research.sample_count, 6);
+    assert!(research.cost.raw_error_rate > 0.3);
+    assert!(research.cost.corrected_error_rate < 1e-6);
+    assert!((research.time.slope.expect("time fit") - 2.0).abs() < 1e-9);
+    assert!(resp.categories[0].cost.slope.is_none());
+
+    let Json(resp) = jobs_estimation_accuracy_handler(
+        State(state),
+        Query(EstimationAccuracyQuery {
+            category: Some("drafting".to_string()),
+        }),
+    )
+    .await
+    .expect("filtered accuracy");
+    assert_eq!(resp.categories.len(), 1);
+    assert_eq!(resp.categories[0].sample_count, 1);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_note_changes_broadcast_live_events_and_mentions() {
+    use futures::StreamExt;
+
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    db.ensure_user_account("alice", "Alice", UserRole::Attorney)
+        .await
+        .expect("create member");
+    db.upsert_matter_membership(&crate::db::UpsertMatterMembershipParams {
+        matter_owner_user_id: "test-user".to_string(),
+        matter_id: "demo".to_string(),
+        member_user_id: "alice".to_string(),
+        role: crate::db::MatterMemberRole::Collaborator,
+    })
+    .await
+    .expect("add member");
+    let mut events = Box::pin(state.sse.subscribe_raw().expect("subscribe"));
+    macro_rules! next_event {
+        () => {
+            tokio::time::timeout(std::time::Duration::from_secs(2), events.next())
+                .await
+                .expect("event in time")
+                .expect("stream open")
+        };
+    }
+
+    let (_, Json(note)) = matter_notes_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateMatterNoteRequest {
+            author: "Lead".to_string(),
+            body: "@alice please review the indemnity clause".to_string(),
+            pinned: false,
+            parent_id: None,
+        }),
+    )
+    .await
+    .expect("create note");
+    match next_event!() {
+        SseEvent::MatterNote {
+            matter_id,
+            action,
+            note_id,
+            actor,
+        } => {
+            assert_eq!(matter_id, "demo");
+            assert_eq!(action, "created");
+            assert_eq!(note_id, note.id);
+            assert_eq!(actor, "test-user");
+        }
+        other => panic!("expected matter_note event, got {other:?}"),
+    }
+    match next_event!() {
+        SseEvent::MatterNoteMention {
+            mentioned_user_id,
+            note_id,
+            ..
+        } => {
+            assert_eq!(mentioned_user_id, "alice");
+            assert_eq!(note_id, note.id);
+        }
+        other => panic!("expected mention event, got {other:?}"),
+    }
+
+    // Only mentions added by the edit notify; unknown handles are ignored.
+    let Json(updated) = matter_notes_patch_handler(
+        State(Arc::clone(&state)),
+        principal_with_role("alice", UserRole::Attorney),
+        Path(("demo".to_string(), note.id.clone())),
+        Json(UpdateMatterNoteRequest {
+            author: None,
+            body: Some("@alice reviewed; @test-user @mallory see redline".to_string()),
+            pinned: None,
+        }),
+    )
+    .await
+    .expect("update note");
+    assert!(updated.body.contains("redline"));
+    assert!(matches!(
+        next_event!(),
+        SseEvent::MatterNote { ref action, ref actor, .. } if action == "updated" && actor == "alice"
+    ));
+    assert!(matches!(
+        next_event!(),
+        SseEvent::MatterNoteMention { ref mentioned_user_id, .. } if mentioned_user_id == "test-user"
+    ));
+
+    let status = matter_notes_delete_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(("demo".to_string(), note.id.clone())),
+    )
+    .await
+    .expect("delete note");
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(matches!(
+        next_event!(),
+        SseEvent::MatterNote { ref action, .. } if action == "deleted"
+    ));
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_note_replies_thread_and_mentions_reach_the_inbox() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    db.ensure_user_account("thread-alice", "Alice", UserRole::Attorney)
+        .await
+        .expect("create member");
+    db.upsert_matter_membership(&crate::db::UpsertMatterMembershipParams {
+        matter_owner_user_id: "test-user".to_string(),
+        matter_id: "demo".to_string(),
+        member_user_id: "thread-alice".to_string(),
+        role: crate::db::MatterMemberRole::Collaborator,
+    })
+    .await
+    .expect("add member");
+    let alice = || principal_with_role("thread-alice", UserRole::Attorney);
+    let Json(delivery) = notification_delivery_set_handler(
+        State(Arc::clone(&state)),
+        alice(),
+        Json(NotificationDeliveryInfo {
+            channel: " Telegram ".to_string(),
+            recipient: Some("alice-chat-42".to_string()),
+        }),
+    )
+    .await
+    .expect("set delivery channel");
+    assert_eq!(delivery.channel, "telegram");
+    let mut deliveries = crate::notifications::subscribe_deliveries();
+
+    let note = |body: &str, parent_id: Option<String>| CreateMatterNoteRequest {
+        author: "Lead".to_string(),
+        body: body.to_string(),
+        pinned: false,
+        parent_id,
+    };
+    let (_, Json(root)) = matter_notes_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(note(
+            "@thread-alice can you take the deposition outline?",
+            None,
+        )),
+    )
+    .await
+    .expect("create root note");
+    assert!(root.parent_id.is_none());
+
+    let delivered = loop {
+        let delivery = tokio::time::timeout(std::time::Duration::from_secs(2), deliveries.recv())
+            .await
+            .expect("delivery in time")
+            .expect("delivery channel open");
+        if delivery.recipient == "alice-chat-42" {
+            break delivery;
+        }
+    };
+    assert_eq!(delivered.channel, "telegram");
+    assert!(delivered.message.contains("Lead mentioned you on demo"));
+
+    let Json(inbox) = notifications_list_handler(
+        State(Arc::clone(&state)),
+        alice(),
+        Query(NotificationListQuery {
+            status: None,
+            kind: Some("mention".to_string()),
+            limit: None,
+        }),
+    )
+    .await
+    .expect("alice inbox");
+    assert_eq!(inbox.notifications.len(), 1);
+    assert_eq!(inbox.notifications[0].matter_id.as_deref(), Some("demo"));
+    assert_eq!(
+        inbox.notifications[0].source.as_deref(),
+        Some(format!("note:{}", root.id).as_str())
+    );
+
+    let (_, Json(reply)) = matter_notes_create_handler(
+        State(Arc::clone(&state)),
+        alice(),
+        Path("demo".to_string()),
+        Json(note("On it.", Some(root.id.clone()))),
+    )
+    .await
+    .expect("reply");
+    assert_eq!(reply.parent_id.as_deref(), Some(root.id.as_str()));
+    let (_, Json(nested)) = matter_notes_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(note("Thanks.", Some(reply.id.clone()))),
+    )
+    .await
+    .expect("reply to a reply");
+    assert_eq!(nested.parent_id.as_deref(), Some(root.id.as_str()));
+
+    let err = matter_notes_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(note("Orphan", Some(Uuid::new_v4().to_string()))),
+    )
+    .await
+    .expect_err("unknown parent");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+
+    matter_notes_delete_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(("demo".to_string(), root.id.clone())),
+    )
+    .await
+    .expect("delete thread");
+    let Json(listed) = matter_notes_list_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("list notes");
+    assert!(listed.notes.is_empty());
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn matter_detail_work_and_finance_endpoints_return_expected_data() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+
+    let _ = matter_tasks_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateMatterTaskRequest {
+            title: "Draft chronology".to_string(),
+            description: Some("Capture filing timeline".to_string()),
+            status: Some("todo".to_string()),
+            assignee: Some("Paralegal".to_string()),
+            due_at: None,
+            blocked_by: Vec::new(),
+            parent_task_id: None,
+            checklist: Vec::new(),
+        }),
+    )
+    .await
+    .expect("create task");
+
+    let _ = matter_notes_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateMatterNoteRequest {
+            author: "Lead".to_string(),
+            body: "Initial intake complete".to_string(),
+            pinned: true,
+            parent_id: None,
+        }),
+    )
+    .await
+    .expect("create note");
+
+    let _ = matter_time_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateTimeEntryRequest {
+            timekeeper: "Lead".to_string(),
+            description: "Case strategy review".to_string(),
+            hours: "1.25".to_string(),
+            hourly_rate: Some("300".to_string()),
+            task_code: None,
+            activity_code: None,
+            entry_date: "2026-04-12".to_string(),
+            billable: Some(true),
+            block_billing_flag: None,
+            block_billing_reason: None,
+        }),
+    )
+    .await
+    .expect("create time entry");
+
+    let _ = matter_expenses_create_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(CreateExpenseEntryRequest {
+            submitted_by: "Lead".to_string(),
+            description: "Filing courier".to_string(),
+            amount: "45.00".to_string(),
+            category: "other".to_string(),
+            entry_date: "2026-04-12".to_string(),
+            receipt_path: None,
+            billable: Some(true),
+        }),
+    )
+    .await
+    .expect("create expense entry");
 
-    let err = matter_filing_package_handler(
+    let _ = matter_trust_deposit_handler(
         State(Arc::clone(&state)),
         owner_principal(),
         Path("demo".to_string()),
+        Json(TrustDepositRequest {
+            amount: "500.00".to_string(),
+            recorded_by: "Lead".to_string(),
+            description: Some("Initial retainer".to_string()),
+            reference_number: None,
+        }),
     )
     .await
-    .expect_err("unready filing document should block export");
-    assert_eq!(err.0, StatusCode::CONFLICT);
-    assert!(err.1.contains("not ready to file"));
+    .expect("create trust deposit");
 
-    let Json(verification) = matter_citations_verify_handler(
+    let Json(tasks) = matter_tasks_list_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path("demo".to_string()),
-        Json(VerifyMatterCitationsRequest {
-            matter_document_id: matter_document.id.to_string(),
-            waivers: vec![CitationWaiverRequest {
-                citation_text: "410 U.S. 113 (1973)".to_string(),
-                waived_by: "Lead".to_string(),
-                reason: "Manual cite verification documented separately".to_string(),
-            }],
-        }),
+        Path("demo".into()),
     )
     .await
-    .expect("citation verification should succeed");
-    assert_eq!(verification.readiness_state, "waived");
-    assert_eq!(verification.results.len(), 1);
-    assert_eq!(verification.results[0].status, "waived");
+    .expect("list tasks");
+    assert_eq!(tasks.tasks.len(), 1);
 
-    let Json(citations) = document_citations_handler(
+    let Json(notes) = matter_notes_list_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(matter_document.id.to_string()),
+        Path("demo".into()),
     )
     .await
-    .expect("document citations should load");
-    assert_eq!(citations.readiness_state, "waived");
-    assert_eq!(citations.results.len(), 1);
+    .expect("list notes");
+    assert_eq!(notes.notes.len(), 1);
 
-    let Json(ready) = document_ready_handler(
+    let Json(time_entries) = matter_time_list_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(matter_document.id.to_string()),
-        Json(MarkDocumentReadyRequest {
-            attorney: Some("Lead".to_string()),
-        }),
+        Path("demo".into()),
     )
     .await
-    .expect("ready-to-file transition should succeed");
-    assert_eq!(
-        ready.document.readiness_state.as_deref(),
-        Some("ready_to_file")
-    );
+    .expect("list time entries");
+    assert_eq!(time_entries.entries.len(), 1);
 
-    let (status, Json(resp)) = matter_filing_package_handler(
+    let Json(expense_entries) = matter_expenses_list_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path("demo".to_string()),
+        Path("demo".into()),
     )
     .await
-    .expect("filing package should succeed once document is ready");
-    assert_eq!(status, StatusCode::CREATED);
-    assert!(resp.path.contains("filing-package-"));
-}
+    .expect("list expense entries");
+    assert_eq!(expense_entries.entries.len(), 1);
 
-#[test]
-fn list_matters_root_entries_returns_500_for_storage_errors() {
-    let err = list_matters_root_entries(Err(crate::error::WorkspaceError::SearchFailed {
-        reason: "boom".to_string(),
-    }))
-    .expect_err("search errors should map to 500");
-    assert_eq!(err.0, StatusCode::INTERNAL_SERVER_ERROR);
-    assert!(err.1.contains("Search failed"));
-}
+    let Json(summary) = matter_time_summary_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".into()),
+    )
+    .await
+    .expect("time summary");
+    let total_hours = summary
+        .total_hours
+        .parse::<rust_decimal::Decimal>()
+        .expect("hours decimal");
+    assert_eq!(total_hours, rust_decimal::Decimal::new(125, 2));
 
-#[test]
-fn list_matters_root_entries_allows_document_not_found_as_empty() {
-    let entries = list_matters_root_entries(Err(crate::error::WorkspaceError::DocumentNotFound {
-        doc_type: MATTER_ROOT.to_string(),
-        user_id: "test-user".to_string(),
-    }))
-    .expect("missing matter root should be treated as empty");
-    assert!(entries.is_empty());
+    let Json(ledger) =
+        matter_trust_ledger_handler(State(state), owner_principal(), Path("demo".into()))
+            .await
+            .expect("trust ledger");
+    assert_eq!(ledger.matter_id, "demo");
+    assert_eq!(ledger.entries.len(), 1);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matter_invoices_list_returns_recent_limited_rows() {
+async fn memory_write_handler_invalidates_conflict_cache() {
+    crate::legal::matter::reset_conflict_cache_for_tests();
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+    let mut legal = test_legal_config();
+    legal.require_matter_context = false;
+    let state = test_gateway_state_with_store_workspace_and_legal(
+        Arc::clone(&db),
+        Arc::clone(&workspace),
+        legal,
+    );
+
+    workspace
+        .write(
+            "conflicts.json",
+            r#"[{"name":"Alpha Holdings","aliases":["Alpha"]}]"#,
+        )
         .await
-        .expect("sync matter row");
-    let store = state.store.as_ref().expect("store should exist");
+        .expect("seed conflicts");
 
-    for idx in 1..=3 {
-        let invoice_number = format!("INV-LIST-{idx:03}");
-        let params = crate::db::CreateInvoiceParams {
-            matter_id: "demo".to_string(),
-            invoice_number,
-            status: crate::db::InvoiceStatus::Draft,
-            issued_date: None,
-            due_date: None,
-            subtotal: rust_decimal::Decimal::ZERO,
-            tax: rust_decimal::Decimal::ZERO,
-            total: rust_decimal::Decimal::ZERO,
-            paid_amount: rust_decimal::Decimal::ZERO,
-            notes: Some("List test".to_string()),
-        };
-        store
-            .save_invoice_draft(&state.user_id, &params, &[])
-            .await
-            .expect("save invoice draft");
-    }
+    let mut legal = crate::config::LegalConfig::resolve(&crate::settings::Settings::default())
+        .expect("default legal config should resolve");
+    legal.active_matter = None;
+    legal.enabled = true;
+    legal.conflict_check_enabled = true;
 
-    let Json(resp) = matter_invoices_list_handler(
-        State(Arc::clone(&state)),
+    let first =
+        crate::legal::matter::detect_conflict(workspace.as_ref(), &legal, "Alpha Holdings").await;
+    assert_eq!(first.as_deref(), Some("Alpha Holdings"));
+    assert_eq!(
+        crate::legal::matter::conflict_cache_refresh_count_for_tests(),
+        1
+    );
+
+    let write_result = memory_write_handler(
+        State(state),
         owner_principal(),
-        Path("demo".to_string()),
-        Query(MatterInvoicesQuery { limit: Some(2) }),
+        HeaderMap::new(),
+        Json(MemoryWriteRequest {
+            path: "conflicts.json".to_string(),
+            content: r#"[{"name":"Beta Partners","aliases":["Beta"]}]"#.to_string(),
+        }),
     )
     .await
-    .expect("matter invoices should load");
+    .expect("memory write should succeed");
+    assert_eq!(write_result.path, "conflicts.json");
 
-    assert_eq!(resp.matter_id, "demo");
-    assert_eq!(resp.invoices.len(), 2);
-    let invoice_numbers: std::collections::HashSet<&str> = resp
-        .invoices
-        .iter()
-        .map(|invoice| invoice.invoice_number.as_str())
-        .collect();
-    assert_eq!(invoice_numbers.len(), 2);
-    assert!(invoice_numbers.is_subset(&std::collections::HashSet::from([
-        "INV-LIST-001",
-        "INV-LIST-002",
-        "INV-LIST-003",
-    ])));
+    let second =
+        crate::legal::matter::detect_conflict(workspace.as_ref(), &legal, "Beta Partners").await;
+    assert_eq!(second.as_deref(), Some("Beta Partners"));
+    assert_eq!(
+        crate::legal::matter::conflict_cache_refresh_count_for_tests(),
+        2
+    );
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matter_invoices_list_rejects_invalid_limit_values() {
+async fn memory_search_handler_scopes_to_requested_matter() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+    let state = test_gateway_state_with_store_workspace_and_legal(
+        Arc::clone(&db),
+        Arc::clone(&workspace),
+        test_legal_config(),
+    );
+
+    workspace
+        .write("matters/acme/notes.md", "Indemnity cap negotiation")
         .await
-        .expect("sync matter row");
+        .expect("seed acme");
+    workspace
+        .write("matters/acme-2/notes.md", "Indemnity carve-outs")
+        .await
+        .expect("seed acme-2");
+
+    let Json(scoped) = memory_search_handler(
+        State(Arc::clone(&state)),
+        Json(MemorySearchRequest {
+            query: "Indemnity".to_string(),
+            limit: None,
+            matter_id: Some("Acme".to_string()),
+            tags: Vec::new(),
+        }),
+    )
+    .await
+    .expect("scoped search");
+    assert_eq!(scoped.results.len(), 1);
+    assert!(scoped.results[0].content.contains("cap negotiation"));
+    assert_eq!(scoped.results[0].path, "matters/acme/notes.md");
+
+    let Json(unscoped) = memory_search_handler(
+        State(Arc::clone(&state)),
+        Json(MemorySearchRequest {
+            query: "Indemnity".to_string(),
+            limit: None,
+            matter_id: None,
+            tags: Vec::new(),
+        }),
+    )
+    .await
+    .expect("unscoped search");
+    assert_eq!(unscoped.results.len(), 2);
+
+    let err = memory_search_handler(
+        State(state),
+        Json(MemorySearchRequest {
+            query: "Indemnity".to_string(),
+            limit: None,
+            matter_id: Some("!!!".to_string()),
+            tags: Vec::new(),
+        }),
+    )
+    .await
+    .expect_err("empty matter id should be rejected");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn document_tags_filter_listing_search_and_facets() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    let state = test_gateway_state_with_store_workspace_and_legal(
+        Arc::clone(&db),
+        Arc::clone(&workspace),
+        test_legal_config(),
+    );
+    let hot = "matters/acme/discovery/email-0412.md";
+    let cold = "matters/acme/discovery/email-0413.md";
+    for (path, body) in [
+        (hot, "Indemnity cap was never approved by the board"),
+        (cold, "Indemnity schedule attached for lunch planning"),
+    ] {
+        workspace.write(path, body).await.expect("seed doc");
+    }
+
+    let tag = |key: &str, value: Option<&str>| DocumentTagInput {
+        key: key.to_string(),
+        value: value.map(str::to_string),
+    };
+    let Json(tagged) = memory_set_tags_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(SetDocumentTagsRequest {
+            path: hot.to_string(),
+            tags: vec![
+                tag("Hot Doc", None),
+                tag("privilege", Some("attorney-client")),
+            ],
+        }),
+    )
+    .await
+    .expect("tag hot doc");
+    assert_eq!(tagged.tags.len(), 2);
+    assert_eq!(tagged.tags[0].key, "hot-doc");
+    let _ = memory_set_tags_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(SetDocumentTagsRequest {
+            path: cold.to_string(),
+            tags: vec![tag("privilege", Some("none"))],
+        }),
+    )
+    .await
+    .expect("tag cold doc");
+
+    let err = memory_set_tags_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Json(SetDocumentTagsRequest {
+            path: "matters/acme/missing.md".to_string(),
+            tags: vec![tag("hot-doc", None)],
+        }),
+    )
+    .await
+    .expect_err("missing document");
+    assert_eq!(err.0, StatusCode::NOT_FOUND);
+
+    let Json(listing) = memory_list_handler(
+        State(Arc::clone(&state)),
+        Query(crate::channels::web::server::ListQuery {
+            path: Some("matters/acme/discovery".to_string()),
+            tag: Some("hot-doc".to_string()),
+        }),
+    )
+    .await
+    .expect("filtered listing");
+    let paths: Vec<_> = listing.entries.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(paths, vec![hot]);
+
+    let Json(dirs) = memory_list_handler(
+        State(Arc::clone(&state)),
+        Query(crate::channels::web::server::ListQuery {
+            path: Some("matters/acme".to_string()),
+            tag: Some("privilege=attorney-client".to_string()),
+        }),
+    )
+    .await
+    .expect("filtered directory listing");
+    assert!(
+        dirs.entries
+            .iter()
+            .all(|e| e.is_dir && e.path.ends_with("discovery"))
+    );
+    assert_eq!(dirs.entries.len(), 1);
 
-    let err = matter_invoices_list_handler(
+    let Json(found) = memory_search_handler(
         State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-        Query(MatterInvoicesQuery { limit: Some(0) }),
+        Json(MemorySearchRequest {
+            query: "Indemnity".to_string(),
+            limit: None,
+            matter_id: Some("acme".to_string()),
+            tags: vec!["privilege=attorney-client".to_string()],
+        }),
     )
     .await
-    .expect_err("limit=0 should fail");
-    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    .expect("tag-filtered search");
+    assert_eq!(found.results.len(), 1);
+    assert_eq!(found.results[0].path, hot);
 
-    let err = matter_invoices_list_handler(
+    let Json(facets) = memory_tag_facets_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path("demo".to_string()),
-        Query(MatterInvoicesQuery { limit: Some(101) }),
+        Query(DocumentTagFacetsQuery {
+            prefix: Some("matters/acme/".to_string()),
+        }),
     )
     .await
-    .expect_err("limit above max should fail");
-    assert_eq!(err.0, StatusCode::BAD_REQUEST);
-}
-
-#[cfg(feature = "libsql")]
-#[tokio::test]
-async fn matter_invoices_list_blocks_non_owner_access() {
-    let (db, _tmp) = crate::testing::test_db().await;
-    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
-        .await
-        .expect("sync matter row");
-
-    // Build a state whose owner is "other-user" — a different user than the
-    // matter owner ("test-user") so the RBAC guard rejects the request.
-    let other_state =
-        test_gateway_state_for_user(Arc::clone(&db), Arc::clone(&workspace), "other-user");
+    .expect("facets");
+    let facets: Vec<_> = facets
+        .facets
+        .iter()
+        .map(|f| (f.key.as_str(), f.value.as_deref(), f.documents))
+        .collect();
+    assert_eq!(
+        facets,
+        vec![
+            ("hot-doc", None, 1),
+            ("privilege", Some("attorney-client"), 1),
+            ("privilege", Some("none"), 1),
+        ]
+    );
 
-    let err = matter_invoices_list_handler(
-        State(other_state),
+    let status = memory_delete_tag_handler(
+        State(Arc::clone(&state)),
         owner_principal(),
-        Path("demo".to_string()),
-        Query(MatterInvoicesQuery { limit: Some(10) }),
+        Query(DeleteDocumentTagQuery {
+            path: hot.to_string(),
+            key: "hot-doc".to_string(),
+        }),
     )
     .await
-    .expect_err("non-owner should not access matter invoices");
-    // RBAC guard fires before matter lookup: returns FORBIDDEN (not NOT_FOUND).
+    .expect("delete tag");
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let Json(remaining) = memory_tags_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Query(DocumentTagsQuery {
+            path: hot.to_string(),
+        }),
+    )
+    .await
+    .expect("list tags");
+    assert_eq!(remaining.tags.len(), 1);
+
+    let err = memory_tag_facets_handler(
+        State(state),
+        principal_with_role("associate", UserRole::Attorney),
+        Query(DocumentTagFacetsQuery { prefix: None }),
+    )
+    .await
+    .expect_err("non-owner facets across the workspace");
     assert_eq!(err.0, StatusCode::FORBIDDEN);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn invoices_finalize_marks_entries_billed_and_supports_trust_payment() {
-    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
-    crate::legal::audit::clear_test_events();
+async fn memory_write_if_match_rejects_stale_writes_with_merge_preview() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
     let state =
         test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
-        .await
-        .expect("sync matter row");
-    let store = state.store.as_ref().expect("store should exist");
-
-    let time_entry = store
-        .create_time_entry(
-            &state.user_id,
-            "demo",
-            &crate::db::CreateTimeEntryParams {
-                timekeeper: "Lead".to_string(),
-                description: "Motion draft".to_string(),
-                hours: rust_decimal::Decimal::new(150, 2),
-                hourly_rate: Some(rust_decimal::Decimal::new(20000, 2)),
-                task_code: None,
-                activity_code: None,
-                resolved_rate: None,
-                rate_source: None,
-                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 1).expect("valid date"),
-                billable: true,
-                block_billing_flag: false,
-                block_billing_reason: None,
-            },
-        )
-        .await
-        .expect("seed time entry");
-    let expense_entry = store
-        .create_expense_entry(
-            &state.user_id,
-            "demo",
-            &crate::db::CreateExpenseEntryParams {
-                submitted_by: "Lead".to_string(),
-                description: "Filing fee".to_string(),
-                amount: rust_decimal::Decimal::new(4000, 2),
-                category: crate::db::ExpenseCategory::FilingFee,
-                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 1).expect("valid date"),
-                receipt_path: None,
-                billable: true,
-            },
-        )
+    workspace
+        .write("matters/demo/brief.md", "Intro\nArgument\n")
         .await
-        .expect("seed expense entry");
+        .expect("seed draft");
+    db.set_setting(
+        "test-user",
+        MATTER_ACTIVE_SETTING,
+        &serde_json::Value::String("demo".to_string()),
+    )
+    .await
+    .expect("set active matter");
 
-    let (created_status, Json(created)) = invoices_save_handler(
+    let (headers, Json(read)) = memory_read_handler(
         State(Arc::clone(&state)),
-        owner_principal(),
-        Json(DraftInvoiceRequest {
-            matter_id: "demo".to_string(),
-            invoice_number: "INV-1001".to_string(),
-            due_date: Some("2026-05-30".to_string()),
-            notes: Some("Initial billing cycle".to_string()),
+        Query(crate::channels::web::server::ReadQuery {
+            path: "matters/demo/brief.md".to_string(),
         }),
     )
     .await
-    .expect("save draft should succeed");
-    assert_eq!(created_status, StatusCode::CREATED);
-    assert_eq!(created.invoice.status, "draft");
-    assert_eq!(created.line_items.len(), 2);
-    let invoice_id = created.invoice.id.clone();
+    .expect("read draft");
+    assert_eq!(headers[0].1, format!("\"{}\"", read.etag));
 
-    let Json(finalized) = invoices_finalize_handler(
+    // Someone else edits the draft after our read.
+    workspace
+        .write("matters/demo/brief.md", "Intro\nArgument\nConclusion\n")
+        .await
+        .expect("concurrent edit");
+
+    let mut if_match = HeaderMap::new();
+    if_match.insert(
+        axum::http::header::IF_MATCH,
+        format!("\"{}\"", read.etag).parse().unwrap(),
+    );
+    let err = memory_write_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(invoice_id.clone()),
+        if_match,
+        Json(MemoryWriteRequest {
+            path: "matters/demo/brief.md".to_string(),
+            content: "Intro (revised)\nArgument\n".to_string(),
+        }),
     )
     .await
-    .expect("finalize should succeed");
-    assert_eq!(finalized.invoice.status, "sent");
-
-    let invoice_uuid = Uuid::parse_str(&invoice_id).expect("invoice uuid");
-    let time_after = store
-        .get_time_entry(&state.user_id, "demo", time_entry.id)
-        .await
-        .expect("get time entry")
-        .expect("time entry exists");
-    let expense_after = store
-        .get_expense_entry(&state.user_id, "demo", expense_entry.id)
+    .expect_err("stale write");
+    assert_eq!(err.status(), StatusCode::CONFLICT);
+    let body = axum::body::to_bytes(err.into_body(), usize::MAX)
         .await
-        .expect("get expense entry")
-        .expect("expense entry exists");
-    let invoice_id_str = invoice_uuid.to_string();
+        .expect("conflict body");
+    let preview: serde_json::Value = serde_json::from_slice(&body).expect("conflict json");
+    assert_eq!(preview["current_content"], "Intro\nArgument\nConclusion\n");
     assert_eq!(
-        time_after.billed_invoice_id.as_deref(),
-        Some(invoice_id_str.as_str())
+        preview["changes_since_read"],
+        " Intro\n Argument\n+Conclusion\n"
     );
     assert_eq!(
-        expense_after.billed_invoice_id.as_deref(),
-        Some(invoice_id_str.as_str())
+        preview["proposed_changes"],
+        "-Intro\n+Intro (revised)\n Argument\n-Conclusion\n"
+    );
+    assert_eq!(
+        workspace
+            .read("matters/demo/brief.md")
+            .await
+            .unwrap()
+            .content,
+        "Intro\nArgument\nConclusion\n"
     );
 
-    let (deposit_status, _deposit_body) = matter_trust_deposit_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-        Json(TrustDepositRequest {
-            amount: "500.00".to_string(),
-            recorded_by: "Lead".to_string(),
-            description: Some("Retainer deposit".to_string()),
-            reference_number: None,
-        }),
-    )
-    .await
-    .expect("trust deposit should succeed");
-    assert_eq!(deposit_status, StatusCode::CREATED);
-
-    let Json(payment) = invoices_payment_handler(
-        State(Arc::clone(&state)),
+    let mut if_match = HeaderMap::new();
+    if_match.insert(
+        axum::http::header::IF_MATCH,
+        preview["current_etag"].as_str().unwrap().parse().unwrap(),
+    );
+    let Json(written) = memory_write_handler(
+        State(state),
         owner_principal(),
-        Path(invoice_id.clone()),
-        Json(RecordInvoicePaymentRequest {
-            amount: "50.00".to_string(),
-            recorded_by: "Lead".to_string(),
-            draw_from_trust: true,
-            description: Some("Apply trust funds".to_string()),
+        if_match,
+        Json(MemoryWriteRequest {
+            path: "matters/demo/brief.md".to_string(),
+            content: "Intro (revised)\nArgument\nConclusion\n".to_string(),
         }),
     )
     .await
-    .expect("payment should succeed");
-    let paid = payment
-        .invoice
-        .paid_amount
-        .parse::<rust_decimal::Decimal>()
-        .expect("paid amount should parse");
-    assert_eq!(paid, rust_decimal::Decimal::new(5000, 2));
-    assert!(payment.trust_entry.is_some());
-
-    let Json(ledger) =
-        matter_trust_ledger_handler(State(state), owner_principal(), Path("demo".to_string()))
-            .await
-            .expect("ledger should load");
-    let balance = ledger
-        .balance
-        .parse::<rust_decimal::Decimal>()
-        .expect("balance should parse");
-    assert_eq!(balance, rust_decimal::Decimal::new(45000, 2));
-    assert_eq!(ledger.entries.len(), 2);
-
-    let events = crate::legal::audit::test_events_snapshot();
-    assert!(
-        events
-            .iter()
-            .any(|event| event.event_type == "invoice_finalized")
-    );
-    assert!(
-        events
-            .iter()
-            .any(|event| event.event_type == "payment_recorded")
-    );
-    assert!(
-        events
-            .iter()
-            .any(|event| event.event_type == "trust_deposit")
-    );
+    .expect("write with current etag");
+    assert_ne!(written.etag, read.etag);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn invoices_payment_rejects_trust_overdraw() {
-    let _audit_lock = crate::legal::audit::lock_test_event_scenario().await;
-    crate::legal::audit::clear_test_events();
+async fn memory_history_lists_revisions_and_restores_earlier_content() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
-        .await
-        .expect("sync matter row");
-    let store = state.store.as_ref().expect("store should exist");
-
-    store
-        .create_expense_entry(
-            &state.user_id,
-            "demo",
-            &crate::db::CreateExpenseEntryParams {
-                submitted_by: "Lead".to_string(),
-                description: "Service fee".to_string(),
-                amount: rust_decimal::Decimal::new(10000, 2),
-                category: crate::db::ExpenseCategory::Other,
-                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 2).expect("valid date"),
-                receipt_path: None,
-                billable: true,
-            },
-        )
-        .await
-        .expect("seed expense entry");
+    let state = test_gateway_state_with_store_workspace_and_legal(
+        Arc::clone(&db),
+        Arc::clone(&workspace),
+        test_legal_config(),
+    );
+    let path = "matters/acme/research/memo.md";
+    for content in ["Limitations: 2 years", "Limitations: 3 years"] {
+        workspace.write(path, content).await.expect("write memo");
+    }
 
-    let (_status, Json(created)) = invoices_save_handler(
+    let Json(history) = memory_history_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(DraftInvoiceRequest {
-            matter_id: "demo".to_string(),
-            invoice_number: "INV-2001".to_string(),
-            due_date: Some("2026-06-01".to_string()),
-            notes: None,
+        Query(MemoryHistoryQuery {
+            path: path.to_string(),
+            revision: None,
+        }),
+    )
+    .await
+    .expect("history");
+    let numbers: Vec<_> = history.revisions.iter().map(|r| r.revision).collect();
+    assert_eq!(numbers, vec![2, 1]);
+    assert_eq!(
+        history.revisions[0].diff.as_deref(),
+        Some("-Limitations: 2 years\n+Limitations: 3 years\n")
+    );
+    assert!(history.revisions[0].content.is_none());
+
+    let Json(single) = memory_history_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Query(MemoryHistoryQuery {
+            path: path.to_string(),
+            revision: Some(1),
         }),
     )
     .await
-    .expect("save draft should succeed");
-    let _ = invoices_finalize_handler(
+    .expect("single revision");
+    assert_eq!(
+        single.revisions[0].content.as_deref(),
+        Some("Limitations: 2 years")
+    );
+
+    let Json(restored) = memory_history_restore_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(created.invoice.id.clone()),
+        Json(MemoryRestoreRevisionRequest {
+            path: path.to_string(),
+            revision: 1,
+        }),
     )
     .await
-    .expect("finalize should succeed");
+    .expect("restore revision");
+    assert_eq!(restored.restored_revision, 1);
+    assert_eq!(
+        workspace.read(path).await.unwrap().content,
+        "Limitations: 2 years"
+    );
+    let history = workspace
+        .history(path)
+        .await
+        .expect("history after restore");
+    assert_eq!(history.len(), 3, "restore adds a revision");
 
-    let (_deposit_status, _deposit_body) = matter_trust_deposit_handler(
+    let err = memory_history_restore_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path("demo".to_string()),
-        Json(TrustDepositRequest {
-            amount: "10.00".to_string(),
-            recorded_by: "Lead".to_string(),
-            description: Some("Small deposit".to_string()),
-            reference_number: None,
+        Json(MemoryRestoreRevisionRequest {
+            path: path.to_string(),
+            revision: 9,
         }),
     )
     .await
-    .expect("trust deposit should succeed");
+    .expect_err("unknown revision");
+    assert_eq!(err.0, StatusCode::NOT_FOUND);
 
-    let result = invoices_payment_handler(
+    let err = memory_history_handler(
         State(state),
-        owner_principal(),
-        Path(created.invoice.id),
-        Json(RecordInvoicePaymentRequest {
-            amount: "20.00".to_string(),
-            recorded_by: "Lead".to_string(),
-            draw_from_trust: true,
-            description: Some("Attempt overdraw".to_string()),
+        principal_with_role("associate", UserRole::Attorney),
+        Query(MemoryHistoryQuery {
+            path: path.to_string(),
+            revision: None,
         }),
     )
-    .await;
-    let err = result.expect_err("overdraw payment should fail");
-    assert_eq!(err.0, StatusCode::BAD_REQUEST);
-    assert!(err.1.contains("insufficient"));
-    let events = crate::legal::audit::test_events_snapshot();
-    assert!(
-        events
-            .iter()
-            .any(|event| event.event_type == "trust_withdrawal_rejected")
-    );
+    .await
+    .expect_err("non-member history");
+    assert_eq!(err.0, StatusCode::FORBIDDEN);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn trust_statement_import_compute_and_signoff_round_trip() {
+async fn memory_delete_moves_documents_to_restorable_trash() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+    let state = test_gateway_state_with_store_workspace_and_legal(
+        Arc::clone(&db),
+        Arc::clone(&workspace),
+        test_legal_config(),
+    );
+    let memo = "matters/acme/research/memo.md";
+    let notes = "notes/todo.md";
+    workspace
+        .write(memo, "Limitations memo v1")
         .await
-        .expect("sync matter row");
+        .expect("seed memo");
+    workspace
+        .write(notes, "Call opposing counsel")
+        .await
+        .expect("seed notes");
 
-    let Json(account) = trust_account_put_handler(
+    let Json(trashed) = memory_delete_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(UpdateTrustAccountRequest {
-            name: "Primary IOLTA".to_string(),
-            bank_name: Some("Bar Bank".to_string()),
-            account_number_last4: Some("1234".to_string()),
+        Json(MemoryDeleteRequest {
+            path: memo.to_string(),
         }),
     )
     .await
-    .expect("configure trust account");
-    assert_eq!(account.name, "Primary IOLTA");
-
-    let _ = matter_trust_deposit_handler(
+    .expect("delete memo");
+    assert_eq!(trashed.path, memo);
+    assert!(!workspace.exists(memo).await.unwrap());
+    let _ = memory_delete_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path("demo".to_string()),
-        Json(TrustDepositRequest {
-            amount: "500.00".to_string(),
-            recorded_by: "Lead".to_string(),
-            description: Some("Initial retainer".to_string()),
-            reference_number: Some("DEP-001".to_string()),
+        Json(MemoryDeleteRequest {
+            path: notes.to_string(),
         }),
     )
     .await
-    .expect("seed trust deposit");
-
-    let (_status, Json(imported)) = trust_statements_import_handler(
+    .expect("delete notes");
+    let err = memory_delete_handler(
         State(Arc::clone(&state)),
-        Json(ImportTrustStatementRequest {
-            imported_by: "Lead".to_string(),
-            statement_date: Some("2026-04-30".to_string()),
-            csv: "date,description,debit,credit,balance,reference\n2026-04-12,Initial retainer,,500.00,500.00,DEP-001\n"
-                .to_string(),
+        owner_principal(),
+        Json(MemoryDeleteRequest {
+            path: notes.to_string(),
         }),
     )
     .await
-    .expect("statement import should succeed");
-    assert_eq!(imported.statement.row_count, 1);
+    .expect_err("already deleted");
+    assert_eq!(err.0, StatusCode::NOT_FOUND);
 
-    let (_status, Json(reconciliation)) = trust_reconciliations_compute_handler(
+    let Json(trash) = memory_trash_list_handler(State(Arc::clone(&state)), owner_principal())
+        .await
+        .expect("owner trash");
+    assert_eq!(trash.entries.len(), 2);
+    let Json(trash) = memory_trash_list_handler(
         State(Arc::clone(&state)),
-        Json(ComputeTrustReconciliationRequest {
-            statement_import_id: imported.statement.id.clone(),
-        }),
+        principal_with_role("associate", UserRole::Attorney),
     )
     .await
-    .expect("reconciliation compute should succeed");
-    assert_eq!(reconciliation.status, "draft");
-    assert_eq!(
-        reconciliation
-            .difference
-            .parse::<rust_decimal::Decimal>()
-            .expect("parse reconciliation difference"),
-        rust_decimal::Decimal::ZERO
+    .expect("member trash");
+    assert!(
+        trash.entries.is_empty(),
+        "non-members see no matter or personal entries"
     );
 
-    let Json(signed_off) = trust_reconciliations_signoff_handler(
+    workspace
+        .write(memo, "Limitations memo v2")
+        .await
+        .expect("rewrite memo");
+    let err = memory_trash_restore_handler(
         State(Arc::clone(&state)),
-        Path(reconciliation.id.clone()),
-        Json(SignoffTrustReconciliationRequest {
-            signed_off_by: "Lead".to_string(),
-        }),
+        owner_principal(),
+        Path(trashed.id.clone()),
     )
     .await
-    .expect("reconciliation signoff should succeed");
-    assert_eq!(signed_off.status, "signed_off");
-    assert_eq!(signed_off.signed_off_by.as_deref(), Some("Lead"));
-
-    let Json(ledger) = matter_trust_ledger_handler(
+    .expect_err("path is occupied");
+    assert_eq!(err.0, StatusCode::CONFLICT);
+    workspace.delete(memo).await.expect("clear new memo");
+    let Json(restored) = memory_trash_restore_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path("demo".to_string()),
+        Path(trashed.id.clone()),
     )
     .await
-    .expect("trust ledger should load");
+    .expect("restore memo");
+    assert_eq!(restored.status, "restored");
     assert_eq!(
-        ledger
-            .latest_reconciliation
-            .as_ref()
-            .expect("latest reconciliation should exist")
-            .id,
-        reconciliation.id
+        workspace.read(memo).await.unwrap().content,
+        "Limitations memo v1"
     );
+
+    let Json(trash) = memory_trash_list_handler(State(Arc::clone(&state)), owner_principal())
+        .await
+        .expect("trash after restore");
+    assert_eq!(trash.entries.len(), 1);
+    let status = memory_trash_purge_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(trash.entries[0].id.clone()),
+    )
+    .await
+    .expect("purge notes");
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let err = memory_trash_purge_handler(
+        State(state),
+        owner_principal(),
+        Path(trash.entries[0].id.clone()),
+    )
+    .await
+    .expect_err("already purged");
+    assert_eq!(err.0, StatusCode::NOT_FOUND);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn invoices_payment_rejects_draft_invoice_status() {
+async fn chunk_citations_resolve_to_highlightable_passages() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
     let state =
         test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
-        .await
-        .expect("sync matter row");
-    let store = state.store.as_ref().expect("store should exist");
 
-    store
-        .create_expense_entry(
-            &state.user_id,
-            "demo",
-            &crate::db::CreateExpenseEntryParams {
-                submitted_by: "Lead".to_string(),
-                description: "Draft-only charge".to_string(),
-                amount: rust_decimal::Decimal::new(10000, 2),
-                category: crate::db::ExpenseCategory::Other,
-                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 6).expect("valid date"),
-                receipt_path: None,
-                billable: true,
-            },
+    workspace
+        .write(
+            "matters/acme/lease.md",
+            "# Lease\n\nRent  is due on the\nfirst of each month.",
         )
         .await
-        .expect("seed expense entry");
+        .expect("seed lease");
+    let hit = workspace
+        .search("rent", 1)
+        .await
+        .expect("search")
+        .pop()
+        .expect("hit");
 
-    let (_status, Json(created)) = invoices_save_handler(
+    let stale = Uuid::new_v4();
+    let answer = format!(
+        "Rent is due monthly {} (see also {}).",
+        hit.anchor(),
+        crate::workspace::chunk_anchor(stale)
+    );
+    let citations = resolve_chunk_citations(state.as_ref(), &answer).await;
+    assert_eq!(citations.len(), 1, "unknown chunks are dropped");
+    assert_eq!(citations[0].chunk_id, hit.chunk_id.to_string());
+    assert_eq!(citations[0].path, "matters/acme/lease.md");
+
+    let Json(chunk) =
+        memory_chunk_handler(State(Arc::clone(&state)), Path(hit.chunk_id.to_string()))
+            .await
+            .expect("chunk lookup");
+    assert_eq!(chunk.path, "matters/acme/lease.md");
+    assert_eq!(
+        chunk.passage.as_deref(),
+        Some("# Lease\n\nRent  is due on the\nfirst of each month.")
+    );
+
+    let err = memory_chunk_handler(State(Arc::clone(&state)), Path(stale.to_string()))
+        .await
+        .expect_err("unknown chunk");
+    assert_eq!(err.0, StatusCode::NOT_FOUND);
+    let err = memory_chunk_handler(State(state), Path("nope".to_string()))
+        .await
+        .expect_err("malformed id");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn chat_exchange_files_into_matter_as_attributed_memo() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state = test_gateway_state_with_store_workspace_and_legal(
+        Arc::clone(&db),
+        Arc::clone(&workspace),
+        test_legal_config(),
+    );
+
+    let thread_id = Uuid::new_v4();
+    db.ensure_conversation(thread_id, "gateway", "test-user", None)
+        .await
+        .expect("ensure conversation");
+    for (role, content) in [
+        ("user", "Is the non-compete enforceable in Ontario?"),
+        ("assistant", "Likely not; see Shafron v. KRG."),
+        ("user", "Draft a follow-up question for the client."),
+        ("assistant", "Ask when the employee signed the agreement."),
+        ("user", "Unanswered question"),
+    ] {
+        db.add_conversation_message(thread_id, role, content)
+            .await
+            .expect("add message");
+    }
+
+    let Json(resp) = chat_file_to_matter_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(DraftInvoiceRequest {
+        Path(thread_id.to_string()),
+        Json(FileChatToMatterRequest {
             matter_id: "demo".to_string(),
-            invoice_number: "INV-DRAFT-100".to_string(),
-            due_date: Some("2026-06-06".to_string()),
-            notes: None,
+            turns: vec![0],
+            title: Some("Non-compete research".to_string()),
+            attorney: Some("Jane Attorney".to_string()),
         }),
     )
     .await
-    .expect("save draft should succeed");
+    .expect("file selected turn");
+    assert_eq!(resp.matter_id, "demo");
+    assert!(resp.document.path.starts_with("matters/demo/memos/"));
+    assert!(resp.document.path.ends_with("-non-compete-research.md"));
+    assert_eq!(resp.document.category.as_deref(), Some("internal"));
+    assert_eq!(resp.document.readiness_state.as_deref(), Some("draft"));
+
+    let memo = workspace
+        .read(&resp.document.path)
+        .await
+        .expect("memo written")
+        .content;
+    assert!(memo.starts_with("# Memo: Non-compete research\n"));
+    assert!(memo.contains("- **Filed by:** Jane Attorney"));
+    assert!(memo.contains("Is the non-compete enforceable in Ontario?"));
+    assert!(memo.contains("Likely not; see Shafron v. KRG."));
+    assert!(!memo.contains("follow-up question"));
+
+    let Json(latest) = chat_file_to_matter_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(thread_id.to_string()),
+        Json(FileChatToMatterRequest {
+            matter_id: "demo".to_string(),
+            turns: Vec::new(),
+            title: None,
+            attorney: None,
+        }),
+    )
+    .await
+    .expect("file latest answered turn");
+    assert_eq!(
+        latest.document.display_name.as_deref(),
+        Some("Memo: Draft a follow-up question for the client.")
+    );
+    let docs = db
+        .list_matter_documents_db("test-user", "demo")
+        .await
+        .expect("list matter documents");
+    assert_eq!(
+        docs.iter()
+            .filter(|doc| doc.path.starts_with("matters/demo/memos/"))
+            .count(),
+        2
+    );
 
-    let err = invoices_payment_handler(
+    let err = chat_file_to_matter_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(created.invoice.id.clone()),
-        Json(RecordInvoicePaymentRequest {
-            amount: "25.00".to_string(),
-            recorded_by: "Lead".to_string(),
-            draw_from_trust: false,
-            description: Some("Should fail on draft".to_string()),
+        Path(thread_id.to_string()),
+        Json(FileChatToMatterRequest {
+            matter_id: "demo".to_string(),
+            turns: vec![2],
+            title: None,
+            attorney: None,
         }),
     )
     .await
-    .expect_err("payment on draft invoice should fail");
+    .expect_err("unanswered turn");
     assert_eq!(err.0, StatusCode::BAD_REQUEST);
-    assert!(err.1.contains("status 'draft'"));
 
-    let invoice_after = store
-        .get_invoice(
-            &state.user_id,
-            Uuid::parse_str(&created.invoice.id).expect("invoice uuid"),
-        )
+    let err = chat_file_to_matter_handler(
+        State(state),
+        owner_principal(),
+        Path(Uuid::new_v4().to_string()),
+        Json(FileChatToMatterRequest {
+            matter_id: "demo".to_string(),
+            turns: Vec::new(),
+            title: None,
+            attorney: None,
+        }),
+    )
+    .await
+    .expect_err("unknown thread");
+    assert_eq!(err.0, StatusCode::NOT_FOUND);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn memory_upload_into_matter_classifies_and_files_document() {
+    use axum::extract::FromRequest;
+
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state = test_gateway_state_with_store_workspace_and_legal(
+        Arc::clone(&db),
+        Arc::clone(&workspace),
+        test_legal_config(),
+    );
+
+    let body = "--XBOUNDARY\r\n\
+         Content-Disposition: form-data; name=\"matter_id\"\r\n\r\n\
+         demo\r\n\
+         --XBOUNDARY\r\n\
+         Content-Disposition: form-data; name=\"files\"; filename=\"mutual-nda.md\"\r\n\
+         Content-Type: text/plain\r\n\r\n\
+         This Agreement is entered into WHEREAS the parties agree to keep information confidential. Governing law: Ontario.\r\n\
+         --XBOUNDARY--\r\n";
+    let request = axum::http::Request::builder()
+        .method("POST")
+        .header("content-type", "multipart/form-data; boundary=XBOUNDARY")
+        .body(axum::body::Body::from(body))
+        .expect("request");
+    let multipart = axum::extract::Multipart::from_request(request, &())
+        .await
+        .expect("multipart");
+
+    let (status, Json(resp)) =
+        memory_upload_handler(State(Arc::clone(&state)), owner_principal(), multipart)
+            .await
+            .expect("upload should succeed");
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(resp.files.len(), 1);
+    let file = &resp.files[0];
+    assert_eq!(file.path, "matters/demo/contracts/mutual-nda.md");
+    assert_eq!(
+        file.category,
+        Some(crate::db::MatterDocumentCategory::Contract)
+    );
+    assert_eq!(file.suggested_folder, Some("contracts"));
+
+    let docs = db
+        .list_matter_documents_db("test-user", "demo")
         .await
-        .expect("load invoice")
-        .expect("invoice exists");
-    assert_eq!(invoice_after.status, crate::db::InvoiceStatus::Draft);
-    assert_eq!(invoice_after.paid_amount, rust_decimal::Decimal::ZERO);
+        .expect("list matter documents");
+    let registered = docs
+        .iter()
+        .find(|doc| doc.path == "matters/demo/contracts/mutual-nda.md")
+        .expect("upload registered as matter document");
+    assert_eq!(
+        registered.category,
+        crate::db::MatterDocumentCategory::Contract
+    );
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn invoices_payment_rejects_amount_above_remaining_balance() {
+async fn uploaded_contract_entities_are_queued_for_conflict_review() {
+    use axum::extract::FromRequest;
+
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+    db.seed_matter_parties("other-matter", "Globex LLC", &[], None)
         .await
-        .expect("sync matter row");
-    let store = state.store.as_ref().expect("store should exist");
+        .expect("seed other matter");
+    let state = test_gateway_state_with_store_workspace_and_legal(
+        Arc::clone(&db),
+        Arc::clone(&workspace),
+        test_legal_config(),
+    );
 
-    store
-        .create_expense_entry(
-            &state.user_id,
-            "demo",
-            &crate::db::CreateExpenseEntryParams {
-                submitted_by: "Lead".to_string(),
-                description: "Large service".to_string(),
-                amount: rust_decimal::Decimal::new(10000, 2),
-                category: crate::db::ExpenseCategory::Other,
-                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 8).expect("valid date"),
-                receipt_path: None,
-                billable: true,
-            },
-        )
-        .await
-        .expect("seed expense entry");
+    let body = "--XBOUNDARY\r\n\
+         Content-Disposition: form-data; name=\"matter_id\"\r\n\r\n\
+         demo\r\n\
+         --XBOUNDARY\r\n\
+         Content-Disposition: form-data; name=\"files\"; filename=\"supply-agreement.md\"\r\n\
+         Content-Type: text/plain\r\n\r\n\
+         This Agreement is made between Initech Corp. and Globex LLC.\r\n\
+         By: Maria Delgado\r\n\
+         --XBOUNDARY--\r\n";
+    let request = axum::http::Request::builder()
+        .method("POST")
+        .header("content-type", "multipart/form-data; boundary=XBOUNDARY")
+        .body(axum::body::Body::from(body))
+        .expect("request");
+    let multipart = axum::extract::Multipart::from_request(request, &())
+        .await
+        .expect("multipart");
+    let (_, Json(resp)) =
+        memory_upload_handler(State(Arc::clone(&state)), owner_principal(), multipart)
+            .await
+            .expect("upload should succeed");
+    assert_eq!(resp.files[0].party_candidates, Some(3));
 
-    let (_status, Json(created)) = invoices_save_handler(
+    let Json(listed) = matter_party_candidates_list_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(DraftInvoiceRequest {
-            matter_id: "demo".to_string(),
-            invoice_number: "INV-REM-100".to_string(),
-            due_date: Some("2026-06-08".to_string()),
-            notes: None,
+        Path("demo".to_string()),
+        Query(crate::channels::web::types::PartyCandidatesQuery {
+            status: Some("pending".to_string()),
         }),
     )
     .await
-    .expect("save draft should succeed");
-    let invoice_id = created.invoice.id.clone();
-    let _ = invoices_finalize_handler(
+    .expect("list candidates");
+    assert_eq!(listed.candidates.len(), 3);
+    let globex = listed
+        .candidates
+        .iter()
+        .find(|c| c.name == "Globex LLC")
+        .expect("globex candidate");
+    assert_eq!(globex.entity_type, "organization");
+    assert_eq!(
+        globex.source_path,
+        "matters/demo/contracts/supply-agreement.md"
+    );
+    assert!(
+        globex
+            .conflict_hits
+            .iter()
+            .any(|hit| hit.matter_id == "other-matter"),
+        "lurking conflict should surface on the candidate"
+    );
+
+    let maria = listed
+        .candidates
+        .iter()
+        .find(|c| c.name == "Maria Delgado")
+        .expect("signatory candidate");
+    let Json(rejected) = matter_party_candidate_reject_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(invoice_id.clone()),
+        Path(("demo".to_string(), maria.id.clone())),
     )
     .await
-    .expect("finalize should succeed");
+    .expect("reject");
+    assert_eq!(rejected.candidate.status, "rejected");
 
-    let _ = invoices_payment_handler(
+    let Json(accepted) = matter_party_candidate_accept_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(invoice_id.clone()),
-        Json(RecordInvoicePaymentRequest {
-            amount: "60.00".to_string(),
-            recorded_by: "Lead".to_string(),
-            draw_from_trust: false,
-            description: Some("Initial partial payment".to_string()),
+        Path(("demo".to_string(), globex.id.clone())),
+        Json(crate::channels::web::types::AcceptPartyCandidateRequest {
+            role: "adverse".to_string(),
+            name: None,
+            aliases: vec!["Globex".to_string()],
         }),
     )
     .await
-    .expect("first payment should succeed");
+    .expect("accept");
+    assert_eq!(accepted.candidate.status, "accepted");
+    let party = accepted.party.expect("party created");
+    assert_eq!(party.role, "adverse");
+    assert_eq!(
+        accepted.candidate.party_id.as_deref(),
+        Some(party.party_id.as_str())
+    );
 
-    let err = invoices_payment_handler(
+    let err = matter_party_candidate_accept_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(invoice_id.clone()),
-        Json(RecordInvoicePaymentRequest {
-            amount: "50.00".to_string(),
-            recorded_by: "Lead".to_string(),
-            draw_from_trust: false,
-            description: Some("Should exceed remaining".to_string()),
+        Path(("demo".to_string(), globex.id.clone())),
+        Json(crate::channels::web::types::AcceptPartyCandidateRequest {
+            role: "adverse".to_string(),
+            name: None,
+            aliases: Vec::new(),
         }),
     )
     .await
-    .expect_err("payment above remaining should fail");
-    assert_eq!(err.0, StatusCode::BAD_REQUEST);
-    assert!(err.1.contains("exceeds remaining balance"));
+    .expect_err("double accept should conflict");
+    assert_eq!(err.0, StatusCode::CONFLICT);
 
-    let invoice_after = store
-        .get_invoice(
-            &state.user_id,
-            Uuid::parse_str(&invoice_id).expect("invoice uuid"),
-        )
-        .await
-        .expect("load invoice")
-        .expect("invoice exists");
-    assert_eq!(
-        invoice_after.paid_amount,
-        rust_decimal::Decimal::new(6000, 2)
-    );
-    assert_eq!(invoice_after.status, crate::db::InvoiceStatus::Sent);
+    let parties = db.list_matter_parties("demo").await.expect("parties");
+    assert!(parties.iter().any(|p| p.name == "Globex LLC"));
+    assert!(!parties.iter().any(|p| p.name == "Maria Delgado"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn invoices_payment_exact_remaining_marks_invoice_paid() {
+async fn uploaded_document_entities_feed_party_graph_and_conflicts() {
+    use axum::extract::FromRequest;
+
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+    db.seed_matter_parties("other-matter", "Globex LLC", &[], None)
         .await
-        .expect("sync matter row");
-    let store = state.store.as_ref().expect("store should exist");
+        .expect("seed other matter");
+    let state = test_gateway_state_with_store_workspace_and_legal(
+        Arc::clone(&db),
+        Arc::clone(&workspace),
+        test_legal_config(),
+    );
 
-    store
-        .create_expense_entry(
-            &state.user_id,
-            "demo",
-            &crate::db::CreateExpenseEntryParams {
-                submitted_by: "Lead".to_string(),
-                description: "Precision test service".to_string(),
-                amount: rust_decimal::Decimal::new(3000, 2),
-                category: crate::db::ExpenseCategory::Other,
-                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 10).expect("valid date"),
-                receipt_path: None,
-                billable: true,
-            },
-        )
-        .await
-        .expect("seed expense entry");
+    let body = "--XBOUNDARY\r\n\
+         Content-Disposition: form-data; name=\"matter_id\"\r\n\r\n\
+         demo\r\n\
+         --XBOUNDARY\r\n\
+         Content-Disposition: form-data; name=\"files\"; filename=\"supply-agreement.md\"\r\n\
+         Content-Type: text/plain\r\n\r\n\
+         This Agreement dated March 3, 2023 is made between Initech Corp. and Globex LLC.\r\n\
+         Initech Corp. shall pay Globex LLC US$ 250,000 on signing.\r\n\
+         --XBOUNDARY--\r\n";
+    let request = axum::http::Request::builder()
+        .method("POST")
+        .header("content-type", "multipart/form-data; boundary=XBOUNDARY")
+        .body(axum::body::Body::from(body))
+        .expect("request");
+    let multipart = axum::extract::Multipart::from_request(request, &())
+        .await
+        .expect("multipart");
+    let (_, Json(resp)) =
+        memory_upload_handler(State(Arc::clone(&state)), owner_principal(), multipart)
+            .await
+            .expect("upload should succeed");
+    assert_eq!(resp.files[0].party_candidates, Some(2));
 
-    let (_status, Json(created)) = invoices_save_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Json(DraftInvoiceRequest {
-            matter_id: "demo".to_string(),
-            invoice_number: "INV-EXACT-PAID".to_string(),
-            due_date: Some("2026-06-10".to_string()),
-            notes: None,
-        }),
-    )
-    .await
-    .expect("save draft should succeed");
-    let invoice_id = created.invoice.id.clone();
-    let _ = invoices_finalize_handler(
+    let events = crate::legal::audit::test_events_snapshot();
+    assert!(events.iter().any(|entry| {
+        entry.event_type == "conflict_detected"
+            && entry.details.get("source").and_then(|v| v.as_str())
+                == Some("document_entity_extraction")
+            && entry.details.get("source_path").and_then(|v| v.as_str())
+                == Some("matters/demo/contracts/supply-agreement.md")
+    }));
+
+    let Json(all) = matter_entities_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(invoice_id.clone()),
+        Path("demo".to_string()),
+        Query(crate::channels::web::types::MatterEntitiesQuery::default()),
     )
     .await
-    .expect("finalize should succeed");
+    .expect("list entities");
+    let kinds = all
+        .entities
+        .iter()
+        .map(|entity| (entity.kind.as_str(), entity.normalized_value.as_str()))
+        .collect::<Vec<_>>();
+    assert!(kinds.contains(&("amount", "USD 250000.00")), "{kinds:?}");
+    assert!(kinds.contains(&("date", "2023-03-03")), "{kinds:?}");
+    assert!(kinds.contains(&("organization", "globex llc")), "{kinds:?}");
+    let globex = all
+        .nodes
+        .iter()
+        .find(|node| node.id == "globex llc")
+        .expect("globex node");
+    assert_eq!(globex.status, "candidate");
+    assert_eq!(
+        globex.documents,
+        vec!["matters/demo/contracts/supply-agreement.md"]
+    );
+    assert!(all.nodes.iter().any(|node| node.status == "party"));
+    assert!(all.edges.iter().any(|edge| edge.kind == "mentioned_with"
+        && edge.source == "globex llc"
+        && edge.target == "initech corp"));
 
-    let _ = invoices_payment_handler(
+    let Json(amounts) = matter_entities_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(invoice_id.clone()),
-        Json(RecordInvoicePaymentRequest {
-            amount: "29.99".to_string(),
-            recorded_by: "Lead".to_string(),
-            draw_from_trust: false,
-            description: Some("Primary payment".to_string()),
+        Path("demo".to_string()),
+        Query(crate::channels::web::types::MatterEntitiesQuery {
+            kind: Some("amount".to_string()),
         }),
     )
     .await
-    .expect("first payment should succeed");
+    .expect("list amounts");
+    assert_eq!(amounts.entities.len(), 1);
+    assert_eq!(amounts.entities[0].value, "US$ 250,000");
+    assert_eq!(
+        amounts.entities[0].mentions[0].source_path,
+        "matters/demo/contracts/supply-agreement.md"
+    );
 
-    let _ = invoices_payment_handler(
+    let err = matter_entities_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(invoice_id.clone()),
-        Json(RecordInvoicePaymentRequest {
-            amount: "0.01".to_string(),
-            recorded_by: "Lead".to_string(),
-            draw_from_trust: false,
-            description: Some("Exact remaining payment".to_string()),
+        Path("demo".to_string()),
+        Query(crate::channels::web::types::MatterEntitiesQuery {
+            kind: Some("planet".to_string()),
         }),
     )
     .await
-    .expect("second payment should succeed");
-
-    let invoice_after = store
-        .get_invoice(
-            &state.user_id,
-            Uuid::parse_str(&invoice_id).expect("invoice uuid"),
-        )
-        .await
-        .expect("load invoice")
-        .expect("invoice exists");
-    assert_eq!(
-        invoice_after.paid_amount,
-        rust_decimal::Decimal::new(3000, 2)
-    );
-    assert_eq!(invoice_after.status, crate::db::InvoiceStatus::Paid);
+    .expect_err("unknown kind");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn invoices_finalize_rejects_line_items_already_billed_elsewhere() {
+async fn matter_chronology_lists_facts_with_filters() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
@@ -4145,428 +9684,764 @@ async fn invoices_finalize_rejects_line_items_already_billed_elsewhere() {
     ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
         .await
         .expect("sync matter row");
-    let store = state.store.as_ref().expect("store should exist");
-
-    store
-        .create_expense_entry(
+    for (date, description, source, confidence) in [
+        (
+            "2023-10-02",
+            "Notice of breach sent",
+            "matters/demo/correspondence/notice.md",
+            0.7,
+        ),
+        (
+            "2023-03-03",
+            "Supply agreement signed",
+            "matters/demo/contracts/msa.md",
+            0.85,
+        ),
+        (
+            "2024-01-15",
+            "Statement of claim issued",
+            "matters/demo/pleadings/claim.md",
+            0.5,
+        ),
+    ] {
+        db.create_matter_fact(
             &state.user_id,
             "demo",
-            &crate::db::CreateExpenseEntryParams {
-                submitted_by: "Lead".to_string(),
-                description: "Shared charge".to_string(),
-                amount: rust_decimal::Decimal::new(10000, 2),
-                category: crate::db::ExpenseCategory::Other,
-                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 9).expect("valid date"),
-                receipt_path: None,
-                billable: true,
+            &crate::db::CreateMatterFactParams {
+                fact_date: chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+                description: description.to_string(),
+                source_path: source.to_string(),
+                source_locator: Some("line 1".to_string()),
+                source_excerpt: None,
+                confidence,
+                extractor: "heuristic".to_string(),
+                created_by: state.user_id.clone(),
             },
         )
         .await
-        .expect("seed expense entry");
+        .expect("create fact");
+    }
+    let chronology = |query: MatterChronologyQuery| {
+        matter_chronology_handler(
+            State(Arc::clone(&state)),
+            owner_principal(),
+            Path("demo".to_string()),
+            Query(query),
+        )
+    };
 
-    let (_, Json(first)) = invoices_save_handler(
+    let Json(all) = chronology(MatterChronologyQuery::default())
+        .await
+        .expect("list chronology");
+    assert_eq!(all.matter_id, "demo");
+    let dates: Vec<&str> = all.facts.iter().map(|f| f.date.as_str()).collect();
+    assert_eq!(dates, vec!["2023-03-03", "2023-10-02", "2024-01-15"]);
+    assert_eq!(all.facts[0].source_locator.as_deref(), Some("line 1"));
+
+    let Json(filtered) = chronology(MatterChronologyQuery {
+        from: Some("2023-06-01".to_string()),
+        min_confidence: Some(0.6),
+        ..Default::default()
+    })
+    .await
+    .expect("filtered chronology");
+    assert_eq!(filtered.facts.len(), 1);
+    assert_eq!(filtered.facts[0].description, "Notice of breach sent");
+
+    let Json(by_source) = chronology(MatterChronologyQuery {
+        source: Some("/matters/demo/contracts/msa.md".to_string()),
+        ..Default::default()
+    })
+    .await
+    .expect("source chronology");
+    assert_eq!(by_source.facts.len(), 1);
+    assert_eq!(by_source.facts[0].date, "2023-03-03");
+
+    for query in [
+        MatterChronologyQuery {
+            from: Some("03/03/2023".to_string()),
+            ..Default::default()
+        },
+        MatterChronologyQuery {
+            from: Some("2024-01-01".to_string()),
+            to: Some("2023-01-01".to_string()),
+            ..Default::default()
+        },
+        MatterChronologyQuery {
+            min_confidence: Some(1.5),
+            ..Default::default()
+        },
+    ] {
+        let err = chronology(query).await.expect_err("invalid filter");
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    }
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn memory_embedding_status_reports_pending_chunks() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    workspace
+        .write("notes/intake.md", "Client intake summary")
+        .await
+        .expect("seed note");
+    let state = test_gateway_state_with_store_and_workspace(Arc::clone(&db), workspace);
+
+    let Json(resp) = memory_embedding_status_handler(State(state))
+        .await
+        .expect("status handler should succeed");
+    assert!(!resp.enabled);
+    assert!(resp.total_chunks >= 1);
+    assert_eq!(resp.embedded_chunks, 0);
+    assert_eq!(resp.pending_chunks, resp.total_chunks);
+    assert_eq!(resp.percent_complete, 0.0);
+    assert_eq!(
+        resp.backfill.state,
+        crate::workspace::backfill::BackfillState::Idle
+    );
+}
+
+#[tokio::test]
+async fn supervised_mode_holds_external_replies_for_attorney_review() {
+    let channels = crate::channels::ChannelManager::new();
+    let queue = channels.review_queue();
+    queue.set_enabled(true);
+    let mut state = minimal_test_gateway_state(None);
+    Arc::get_mut(&mut state).expect("fresh state").review_queue = Some(Arc::clone(&queue));
+
+    let inbound = crate::channels::IncomingMessage::new("telegram", "client-7", "any update?");
+    channels
+        .respond(
+            &inbound,
+            crate::channels::OutgoingResponse::text("The motion was filed."),
+        )
+        .await
+        .expect("reply is held, not sent");
+    let gateway_msg = crate::channels::IncomingMessage::new("gateway", "test-user", "hi");
+    assert!(!queue.holds(&gateway_msg.channel));
+
+    let staff = principal_with_role("sam", UserRole::Staff);
+    let err = review_drafts_list_handler(
+        State(Arc::clone(&state)),
+        staff,
+        Query(OutboundDraftListQuery { status: None }),
+    )
+    .await
+    .expect_err("staff cannot review");
+    assert_eq!(err.0, StatusCode::FORBIDDEN);
+
+    let Json(list) = review_drafts_list_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(DraftInvoiceRequest {
-            matter_id: "demo".to_string(),
-            invoice_number: "INV-SHARED-1".to_string(),
-            due_date: Some("2026-06-09".to_string()),
-            notes: None,
+        Query(OutboundDraftListQuery { status: None }),
+    )
+    .await
+    .expect("list drafts");
+    assert!(list.supervised_mode);
+    assert_eq!(list.pending, 1);
+    let draft = &list.drafts[0];
+    assert_eq!(
+        (draft.kind, draft.channel.as_str(), draft.recipient.as_str()),
+        ("reply", "telegram", "client-7")
+    );
+
+    let Json(edited) = review_draft_edit_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(draft.id.clone()),
+        Json(OutboundDraftEditRequest {
+            content: "The motion was filed this morning.".to_string(),
         }),
     )
     .await
-    .expect("save first draft");
-    let (_, Json(second)) = invoices_save_handler(
+    .expect("edit draft");
+    assert!(edited.edited);
+    assert_eq!(edited.status, "pending");
+
+    // No telegram channel is registered, so delivery fails and the draft
+    // stays pending for a retry.
+    let err = review_draft_approve_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(DraftInvoiceRequest {
-            matter_id: "demo".to_string(),
-            invoice_number: "INV-SHARED-2".to_string(),
-            due_date: Some("2026-06-10".to_string()),
-            notes: None,
+        Path(draft.id.clone()),
+        Json(OutboundDraftApproveRequest::default()),
+    )
+    .await
+    .expect_err("delivery fails without the channel");
+    assert_eq!(err.0, StatusCode::BAD_GATEWAY);
+
+    let Json(rejected) = review_draft_reject_handler(
+        State(Arc::clone(&state)),
+        principal_with_role("alice", UserRole::Attorney),
+        Path(draft.id.clone()),
+        Json(OutboundDraftRejectRequest {
+            reason: Some("Call the client instead".to_string()),
         }),
     )
     .await
-    .expect("save second draft");
-    assert_eq!(first.line_items.len(), 1);
-    assert_eq!(second.line_items.len(), 1);
+    .expect("reject draft");
+    assert_eq!(rejected.status, "rejected");
+    assert_eq!(rejected.decided_by.as_deref(), Some("alice"));
+
+    let err = review_draft_approve_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(draft.id.clone()),
+        Json(OutboundDraftApproveRequest::default()),
+    )
+    .await
+    .expect_err("decided drafts cannot be approved");
+    assert_eq!(err.0, StatusCode::CONFLICT);
+}
 
-    let _ = invoices_finalize_handler(
+#[tokio::test]
+async fn webhook_endpoints_are_admin_only_and_hide_secrets() {
+    let mut state = minimal_test_gateway_state(None);
+    let webhooks = Arc::new(crate::integrations::WebhookDispatcher::for_tests(
+        None,
+        crate::integrations::webhooks::RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        },
+    ));
+    Arc::get_mut(&mut state).expect("fresh state").webhooks = Some(Arc::clone(&webhooks));
+
+    let create = || CreateWebhookEndpointRequest {
+        name: "Docketing system".to_string(),
+        // Nothing listens on port 9, so test deliveries fail fast.
+        url: "http://127.0.0.1:9/hook".to_string(),
+        events: vec!["deadline.added".to_string(), "matter.created".to_string()],
+    };
+    let err = webhooks_create_handler(
         State(Arc::clone(&state)),
-        owner_principal(),
-        Path(first.invoice.id.clone()),
+        principal_with_role("alice", UserRole::Attorney),
+        Json(create()),
     )
     .await
-    .expect("first finalize should succeed");
+    .expect_err("attorneys cannot configure webhooks");
+    assert_eq!(err.0, StatusCode::FORBIDDEN);
 
-    let err = invoices_finalize_handler(
+    let err = webhooks_create_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(second.invoice.id.clone()),
+        Json(CreateWebhookEndpointRequest {
+            events: vec!["matter.deleted".to_string()],
+            ..create()
+        }),
     )
     .await
-    .expect_err("second finalize should be blocked");
+    .expect_err("unknown events are rejected");
     assert_eq!(err.0, StatusCode::BAD_REQUEST);
-    assert!(err.1.contains("already billed"));
-
-    let second_invoice = store
-        .get_invoice(
-            &state.user_id,
-            Uuid::parse_str(&second.invoice.id).expect("invoice uuid"),
-        )
-        .await
-        .expect("load second invoice")
-        .expect("second invoice exists");
-    assert_eq!(second_invoice.status, crate::db::InvoiceStatus::Draft);
-}
 
-#[cfg(feature = "libsql")]
-#[tokio::test]
-async fn invoices_payment_concurrent_requests_allow_single_winner() {
-    let (db, _tmp) = crate::testing::test_db().await;
-    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
-        .await
-        .expect("sync matter row");
-    let store = state.store.as_ref().expect("store should exist");
+    let (status, Json(created)) =
+        webhooks_create_handler(State(Arc::clone(&state)), owner_principal(), Json(create()))
+            .await
+            .expect("create endpoint");
+    assert_eq!(status, StatusCode::CREATED);
+    let secret = created.secret.clone().expect("secret shown on create");
+    assert!(created.secret_hint.ends_with(&secret[secret.len() - 4..]));
 
-    store
-        .create_expense_entry(
-            &state.user_id,
-            "demo",
-            &crate::db::CreateExpenseEntryParams {
-                submitted_by: "Lead".to_string(),
-                description: "Concurrent payable".to_string(),
-                amount: rust_decimal::Decimal::new(10000, 2),
-                category: crate::db::ExpenseCategory::Other,
-                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 10).expect("valid date"),
-                receipt_path: None,
-                billable: true,
-            },
-        )
+    let Json(list) = webhooks_list_handler(State(Arc::clone(&state)), owner_principal())
         .await
-        .expect("seed expense entry");
+        .expect("list endpoints");
+    assert_eq!(list.endpoints.len(), 1);
+    assert!(list.endpoints[0].secret.is_none());
+    assert!(list.available_events.contains(&"conflict.hit"));
 
-    let (_, Json(created)) = invoices_save_handler(
+    let Json(rotated) = webhooks_update_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(DraftInvoiceRequest {
-            matter_id: "demo".to_string(),
-            invoice_number: "INV-CONCURRENT-1".to_string(),
-            due_date: Some("2026-06-10".to_string()),
-            notes: None,
+        Path(created.id.clone()),
+        Json(UpdateWebhookEndpointRequest {
+            enabled: Some(false),
+            rotate_secret: true,
+            ..Default::default()
         }),
     )
     .await
-    .expect("save draft");
-    let invoice_id = created.invoice.id.clone();
-    let _ = invoices_finalize_handler(
+    .expect("rotate secret");
+    assert!(!rotated.enabled);
+    assert_ne!(rotated.secret.as_deref(), Some(secret.as_str()));
+
+    // Test pings go out even while the endpoint is disabled.
+    let (status, Json(test)) = webhooks_test_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(invoice_id.clone()),
+        Path(created.id.clone()),
     )
     .await
-    .expect("finalize should succeed");
-
-    let barrier = Arc::new(tokio::sync::Barrier::new(3));
-    let barrier_a = Arc::clone(&barrier);
-    let state_a = Arc::clone(&state);
-    let invoice_a = invoice_id.clone();
-    let task_a = tokio::spawn(async move {
-        barrier_a.wait().await;
-        invoices_payment_handler(
-            State(state_a),
-            owner_principal(),
-            Path(invoice_a),
-            Json(RecordInvoicePaymentRequest {
-                amount: "80.00".to_string(),
-                recorded_by: "Lead A".to_string(),
-                draw_from_trust: false,
-                description: Some("Concurrent payment A".to_string()),
-            }),
-        )
-        .await
-    });
-
-    let barrier_b = Arc::clone(&barrier);
-    let state_b = Arc::clone(&state);
-    let invoice_b = invoice_id.clone();
-    let task_b = tokio::spawn(async move {
-        barrier_b.wait().await;
-        invoices_payment_handler(
-            State(state_b),
+    .expect("queue test delivery");
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let mut delivery = None;
+    for _ in 0..100 {
+        let Json(log) = webhooks_deliveries_handler(
+            State(Arc::clone(&state)),
             owner_principal(),
-            Path(invoice_b),
-            Json(RecordInvoicePaymentRequest {
-                amount: "80.00".to_string(),
-                recorded_by: "Lead B".to_string(),
-                draw_from_trust: false,
-                description: Some("Concurrent payment B".to_string()),
-            }),
-        )
-        .await
-    });
-
-    barrier.wait().await;
-    let result_a = task_a.await.expect("task A joined");
-    let result_b = task_b.await.expect("task B joined");
-    let success_count = usize::from(result_a.is_ok()) + usize::from(result_b.is_ok());
-    assert_eq!(
-        success_count, 1,
-        "exactly one concurrent payment should succeed"
-    );
-
-    let invoice_after = store
-        .get_invoice(
-            &state.user_id,
-            Uuid::parse_str(&invoice_id).expect("invoice uuid"),
+            Path(created.id.clone()),
         )
         .await
-        .expect("load invoice")
-        .expect("invoice exists");
+        .expect("delivery log");
+        delivery = log
+            .deliveries
+            .into_iter()
+            .find(|d| d.id.to_string() == test.delivery_id && d.attempts > 0);
+        if delivery.is_some() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    let delivery = delivery.expect("test delivery attempted");
+    assert_eq!(delivery.event, crate::integrations::WebhookEventKind::Test);
     assert_eq!(
-        invoice_after.paid_amount,
-        rust_decimal::Decimal::new(8000, 2),
-        "paid_amount should reflect only one successful concurrent payment"
+        delivery.status,
+        crate::integrations::WebhookDeliveryStatus::Failed
     );
-    assert_eq!(invoice_after.status, crate::db::InvoiceStatus::Sent);
+    assert!(delivery.last_error.is_some());
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn invoices_payment_concurrent_trust_draw_creates_single_debit_entry() {
+async fn firm_dashboard_aggregates_open_matters() {
+    use crate::db::{
+        ClientType, CreateClientParams, CreateMatterDeadlineParams, CreateMatterTaskParams,
+        CreateTimeEntryParams, MatterDeadlineType, MatterStatus, MatterTaskStatus,
+        UpsertMatterParams,
+    };
+    use crate::history::SandboxJobRecord;
+
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
     let state =
         test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+    let now = Utc::now();
+
+    for (matter_id, status) in [
+        ("acme", MatterStatus::Active),
+        ("globex", MatterStatus::Intake),
+        ("old-matter", MatterStatus::Closed),
+    ] {
+        let client = db
+            .create_client(
+                "test-user",
+                &CreateClientParams {
+                    name: format!("{matter_id} client"),
+                    client_type: ClientType::Entity,
+                    email: None,
+                    phone: None,
+                    address: None,
+                    notes: None,
+                },
+            )
+            .await
+            .expect("create client");
+        db.upsert_matter(
+            "test-user",
+            &UpsertMatterParams {
+                matter_id: matter_id.to_string(),
+                client_id: client.id,
+                status,
+                stage: None,
+                practice_area: None,
+                jurisdiction: None,
+                opened_at: None,
+                closed_at: None,
+                assigned_to: Vec::new(),
+                custom_fields: serde_json::json!({}),
+            },
+        )
         .await
-        .expect("sync matter row");
-    let store = state.store.as_ref().expect("store should exist");
-
-    store
-        .create_expense_entry(
-            &state.user_id,
-            "demo",
-            &crate::db::CreateExpenseEntryParams {
-                submitted_by: "Lead".to_string(),
-                description: "Concurrent trust payable".to_string(),
-                amount: rust_decimal::Decimal::new(10000, 2),
-                category: crate::db::ExpenseCategory::Other,
-                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 11).expect("valid date"),
-                receipt_path: None,
+        .expect("upsert matter");
+        for (title, days) in [("Reply brief", 5), ("Expert reports", 30)] {
+            db.create_matter_deadline(
+                "test-user",
+                matter_id,
+                &CreateMatterDeadlineParams {
+                    title: format!("{matter_id}: {title}"),
+                    deadline_type: MatterDeadlineType::CourtDate,
+                    due_at: now + chrono::Duration::days(days),
+                    completed_at: None,
+                    reminder_days: Vec::new(),
+                    rule_ref: None,
+                    computed_from: None,
+                    task_id: None,
+                    explanation: None,
+                    rule_version: None,
+                    is_unsupported: false,
+                },
+            )
+            .await
+            .expect("create deadline");
+        }
+        for (title, status) in [
+            ("Serve responses", MatterTaskStatus::Todo),
+            ("Draft memo", MatterTaskStatus::Done),
+        ] {
+            db.create_matter_task(
+                "test-user",
+                matter_id,
+                &CreateMatterTaskParams {
+                    title: format!("{matter_id}: {title}"),
+                    description: None,
+                    status,
+                    assignee: None,
+                    due_at: Some(now - chrono::Duration::days(2)),
+                    blocked_by: Vec::new(),
+                    parent_task_id: None,
+                    checklist: Vec::new(),
+                },
+            )
+            .await
+            .expect("create task");
+        }
+        db.create_time_entry(
+            "test-user",
+            matter_id,
+            &CreateTimeEntryParams {
+                timekeeper: "ab".to_string(),
+                description: "Research".to_string(),
+                hours: rust_decimal::Decimal::new(15, 1),
+                hourly_rate: None,
+                task_code: None,
+                activity_code: None,
+                resolved_rate: None,
+                rate_source: None,
+                entry_date: now.date_naive(),
                 billable: true,
+                block_billing_flag: false,
+                block_billing_reason: None,
             },
         )
         .await
-        .expect("seed expense entry");
-
-    let (_, Json(created)) = invoices_save_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Json(DraftInvoiceRequest {
-            matter_id: "demo".to_string(),
-            invoice_number: "INV-CONCURRENT-TRUST-1".to_string(),
-            due_date: Some("2026-06-11".to_string()),
-            notes: None,
-        }),
-    )
-    .await
-    .expect("save draft");
-    let invoice_id = created.invoice.id.clone();
-    let _ = invoices_finalize_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path(invoice_id.clone()),
-    )
-    .await
-    .expect("finalize should succeed");
+        .expect("create time entry");
+    }
+    for (task, status) in [
+        ("Summarize depositions", "running"),
+        ("Old run", "completed"),
+    ] {
+        db.save_sandbox_job(&SandboxJobRecord {
+            id: uuid::Uuid::new_v4(),
+            task: task.to_string(),
+            status: status.to_string(),
+            user_id: "test-user".to_string(),
+            project_dir: "/tmp/job".to_string(),
+            success: None,
+            failure_reason: None,
+            created_at: now,
+            started_at: Some(now),
+            completed_at: None,
+            credential_grants_json: "[]".to_string(),
+        })
+        .await
+        .expect("save job");
+    }
+    for _ in 0..crate::agent::BROKEN_TOOL_FAILURE_THRESHOLD {
+        db.record_tool_failure("pacer_lookup", "timeout")
+            .await
+            .expect("record failure");
+    }
 
-    let _ = matter_trust_deposit_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-        Json(TrustDepositRequest {
-            amount: "100.00".to_string(),
-            recorded_by: "Lead".to_string(),
-            description: Some("Concurrent trust funding".to_string()),
-            reference_number: None,
-        }),
+    let Json(dashboard) = dashboard_handler(State(Arc::clone(&state)), owner_principal())
+        .await
+        .expect("dashboard");
+    assert_eq!(dashboard.matter_count, 2);
+    assert_eq!(dashboard.upcoming_deadlines.len(), 2);
+    assert!(
+        dashboard
+            .upcoming_deadlines
+            .iter()
+            .all(|d| d.deadline.title.ends_with("Reply brief") && d.matter_id != "old-matter")
+    );
+    assert_eq!(dashboard.overdue_tasks.len(), 2);
+    assert!(dashboard.overdue_tasks.iter().all(|t| t.days_overdue == 2));
+    assert_eq!(dashboard.unbilled_hours, "3");
+    assert_eq!(dashboard.unbilled_by_matter.len(), 2);
+    assert_eq!(dashboard.active_jobs.len(), 1);
+    assert_eq!(dashboard.active_jobs[0].state, "in_progress");
+    assert_eq!(dashboard.broken_tools.len(), 1);
+    assert_eq!(dashboard.broken_tools[0].name, "pacer_lookup");
+
+    let err = dashboard_handler(
+        State(state),
+        principal_with_role("associate", UserRole::Attorney),
     )
     .await
-    .expect("trust deposit should succeed");
+    .expect_err("non-owner is rejected");
+    assert_eq!(err.0, StatusCode::FORBIDDEN);
+}
 
-    let barrier = Arc::new(tokio::sync::Barrier::new(3));
-    let barrier_a = Arc::clone(&barrier);
-    let state_a = Arc::clone(&state);
-    let invoice_a = invoice_id.clone();
-    let task_a = tokio::spawn(async move {
-        barrier_a.wait().await;
-        invoices_payment_handler(
-            State(state_a),
-            owner_principal(),
-            Path(invoice_a),
-            Json(RecordInvoicePaymentRequest {
-                amount: "60.00".to_string(),
-                recorded_by: "Lead A".to_string(),
-                draw_from_trust: true,
-                description: Some("Concurrent trust payment A".to_string()),
-            }),
-        )
-        .await
-    });
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn jobs_dead_letter_lists_only_exhausted_sandbox_jobs() {
+    use crate::history::SandboxJobRecord;
 
-    let barrier_b = Arc::clone(&barrier);
-    let state_b = Arc::clone(&state);
-    let invoice_b = invoice_id.clone();
-    let task_b = tokio::spawn(async move {
-        barrier_b.wait().await;
-        invoices_payment_handler(
-            State(state_b),
-            owner_principal(),
-            Path(invoice_b),
-            Json(RecordInvoicePaymentRequest {
-                amount: "60.00".to_string(),
-                recorded_by: "Lead B".to_string(),
-                draw_from_trust: true,
-                description: Some("Concurrent trust payment B".to_string()),
-            }),
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    let state = test_gateway_state_with_store_and_workspace(Arc::clone(&db), workspace);
+    let now = chrono::Utc::now();
+
+    let mut ids = Vec::new();
+    for (task, status, user) in [
+        ("Pull docket", "dead_letter", "test-user"),
+        ("Draft memo", "failed", "test-user"),
+        ("Other user's job", "dead_letter", "someone-else"),
+    ] {
+        let id = uuid::Uuid::new_v4();
+        db.save_sandbox_job(&SandboxJobRecord {
+            id,
+            task: task.to_string(),
+            status: "running".to_string(),
+            user_id: user.to_string(),
+            project_dir: "/tmp/job".to_string(),
+            success: None,
+            failure_reason: None,
+            created_at: now,
+            started_at: Some(now),
+            completed_at: None,
+            credential_grants_json: "[]".to_string(),
+        })
+        .await
+        .expect("save job");
+        db.update_sandbox_job_status(
+            id,
+            status,
+            Some(false),
+            Some("HTTP 429 (gave up after 3 attempts)"),
+            None,
+            Some(now),
         )
         .await
-    });
+        .expect("update status");
+        ids.push(id);
+    }
 
-    barrier.wait().await;
-    let result_a = task_a.await.expect("task A joined");
-    let result_b = task_b.await.expect("task B joined");
-    let success_count = usize::from(result_a.is_ok()) + usize::from(result_b.is_ok());
+    let Json(resp) = jobs_dead_letter_handler(State(Arc::clone(&state)))
+        .await
+        .expect("dead-letter list");
+    assert_eq!(resp.jobs.len(), 1);
+    assert_eq!(resp.jobs[0].id, ids[0]);
+    assert_eq!(resp.jobs[0].title, "Pull docket");
     assert_eq!(
-        success_count, 1,
-        "exactly one concurrent trust draw should succeed"
+        resp.jobs[0].failure_reason.as_deref(),
+        Some("HTTP 429 (gave up after 3 attempts)")
     );
 
-    let invoice_after = store
-        .get_invoice(
-            &state.user_id,
-            Uuid::parse_str(&invoice_id).expect("invoice uuid"),
-        )
+    let summary = db
+        .sandbox_job_summary_for_user("test-user")
         .await
-        .expect("load invoice")
-        .expect("invoice exists");
-    assert_eq!(
-        invoice_after.paid_amount,
-        rust_decimal::Decimal::new(6000, 2)
-    );
+        .expect("summary");
+    assert_eq!(summary.failed, 2);
+}
 
-    let ledger = store
-        .list_trust_ledger_entries(&state.user_id, "demo")
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn job_log_stream_resumes_after_last_event_id_and_closes_when_done() {
+    use crate::history::SandboxJobRecord;
+    use axum::response::IntoResponse;
+
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    let state = test_gateway_state_with_store_and_workspace(Arc::clone(&db), workspace);
+
+    let job_id = Uuid::new_v4();
+    db.save_sandbox_job(&SandboxJobRecord {
+        id: job_id,
+        task: "Index exhibits".to_string(),
+        status: "completed".to_string(),
+        user_id: "test-user".to_string(),
+        project_dir: "/tmp/job".to_string(),
+        success: Some(true),
+        failure_reason: None,
+        created_at: chrono::Utc::now(),
+        started_at: None,
+        completed_at: None,
+        credential_grants_json: "[]".to_string(),
+    })
+    .await
+    .expect("save job");
+    for line in ["first line", "second line", "third line"] {
+        db.save_job_event(
+            job_id,
+            "log",
+            &serde_json::json!({ "stream": "stdout", "line": line }),
+        )
         .await
-        .expect("load trust ledger");
-    let invoice_payment_count = ledger
-        .iter()
-        .filter(|entry| entry.entry_type == crate::db::TrustLedgerEntryType::InvoicePayment)
-        .count();
-    assert_eq!(invoice_payment_count, 1);
-    let balance = store
-        .current_trust_balance(&state.user_id, "demo")
+        .expect("save event");
+    }
+    let events = db.list_job_events(job_id, None).await.expect("events");
+
+    let mut headers = axum::http::HeaderMap::new();
+    headers.insert("last-event-id", events[0].id.to_string().parse().unwrap());
+    let response = jobs_logs_stream_handler(
+        State(Arc::clone(&state)),
+        Path(job_id.to_string()),
+        Query(JobLogStreamQuery::default()),
+        headers,
+    )
+    .await
+    .expect("stream")
+    .into_response();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
         .await
-        .expect("current trust balance");
-    assert_eq!(balance, rust_decimal::Decimal::new(4000, 2));
+        .expect("body");
+    let body = String::from_utf8(body.to_vec()).unwrap();
+
+    assert!(!body.contains("first line"), "{body}");
+    assert!(body.contains("second line"), "{body}");
+    assert!(body.contains("third line"), "{body}");
+    assert!(body.contains(&format!("id: {}", events[2].id)), "{body}");
+    assert!(body.contains("event: done"), "{body}");
+    assert!(body.contains(r#"{"status":"completed"}"#), "{body}");
+
+    let err = jobs_logs_stream_handler(
+        State(Arc::clone(&state)),
+        Path(Uuid::new_v4().to_string()),
+        Query(JobLogStreamQuery::default()),
+        axum::http::HeaderMap::new(),
+    )
+    .await
+    .err()
+    .expect("unknown job");
+    assert_eq!(err.0, StatusCode::NOT_FOUND);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn invoices_void_rejects_paid_invoice_status() {
+async fn job_artifacts_are_listed_downloaded_and_promoted_into_matter() {
+    use crate::history::SandboxJobRecord;
+    use axum::response::IntoResponse;
+
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
-        .await
-        .expect("sync matter row");
-    let store = state.store.as_ref().expect("store should exist");
+    let state = test_gateway_state_with_store_workspace_and_legal(
+        Arc::clone(&db),
+        Arc::clone(&workspace),
+        test_legal_config(),
+    );
 
-    store
-        .create_expense_entry(
-            &state.user_id,
-            "demo",
-            &crate::db::CreateExpenseEntryParams {
-                submitted_by: "Lead".to_string(),
-                description: "Payable service".to_string(),
-                amount: rust_decimal::Decimal::new(10000, 2),
-                category: crate::db::ExpenseCategory::Other,
-                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 7).expect("valid date"),
-                receipt_path: None,
-                billable: true,
-            },
-        )
-        .await
-        .expect("seed expense entry");
+    let project = tempfile::tempdir().expect("project dir");
+    let storage = tempfile::tempdir().expect("artifact storage");
+    std::fs::write(project.path().join("summary.md"), "# Damages summary").unwrap();
+    std::fs::write(project.path().join("chart.png"), [0x89, b'P', b'N', b'G']).unwrap();
 
-    let (_status, Json(created)) = invoices_save_handler(
+    let job_id = Uuid::new_v4();
+    db.save_sandbox_job(&SandboxJobRecord {
+        id: job_id,
+        task: "Summarize damages".to_string(),
+        status: "completed".to_string(),
+        user_id: "test-user".to_string(),
+        project_dir: project.path().display().to_string(),
+        success: Some(true),
+        failure_reason: None,
+        created_at: chrono::Utc::now(),
+        started_at: None,
+        completed_at: None,
+        credential_grants_json: "[]".to_string(),
+    })
+    .await
+    .expect("save job");
+    let registered = crate::orchestrator::artifacts::register_job_artifacts(
+        db.as_ref(),
+        job_id,
+        project.path(),
+        storage.path(),
+    )
+    .await
+    .expect("register artifacts");
+    assert_eq!(registered, 2);
+
+    let Json(list) =
+        jobs_artifacts_list_handler(State(Arc::clone(&state)), Path(job_id.to_string()))
+            .await
+            .expect("list artifacts");
+    let names: Vec<&str> = list.artifacts.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(names, vec!["chart.png", "summary.md"]);
+    assert_eq!(list.artifacts[0].content_type, "image/png");
+    assert_eq!(list.artifacts[1].size_bytes, 17);
+
+    let response = jobs_artifact_download_handler(
         State(Arc::clone(&state)),
-        owner_principal(),
-        Json(DraftInvoiceRequest {
-            matter_id: "demo".to_string(),
-            invoice_number: "INV-PAID-100".to_string(),
-            due_date: Some("2026-06-07".to_string()),
-            notes: None,
-        }),
+        Path((job_id.to_string(), "summary.md".to_string())),
     )
     .await
-    .expect("save draft should succeed");
+    .expect("download")
+    .into_response();
+    assert_eq!(
+        response
+            .headers()
+            .get(axum::http::header::CONTENT_TYPE)
+            .unwrap(),
+        "text/markdown"
+    );
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body");
+    assert_eq!(&body[..], b"# Damages summary");
 
-    let invoice_id = created.invoice.id.clone();
-    let _ = invoices_finalize_handler(
+    let err = jobs_artifact_download_handler(
         State(Arc::clone(&state)),
-        owner_principal(),
-        Path(invoice_id.clone()),
+        Path((job_id.to_string(), "../summary.md".to_string())),
     )
     .await
-    .expect("finalize should succeed");
+    .err()
+    .expect("traversal rejected");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
 
-    let _ = invoices_payment_handler(
+    let Json(promoted) = jobs_artifact_promote_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(invoice_id.clone()),
-        Json(RecordInvoicePaymentRequest {
-            amount: "100.00".to_string(),
-            recorded_by: "Lead".to_string(),
-            draw_from_trust: false,
-            description: Some("Mark paid".to_string()),
+        Path((job_id.to_string(), "summary.md".to_string())),
+        Json(PromoteJobArtifactRequest {
+            matter_id: "demo".to_string(),
         }),
     )
     .await
-    .expect("payment should succeed");
+    .expect("promote");
+    let expected_path = format!(
+        "matters/demo/artifacts/job-{}/summary.md",
+        &job_id.to_string()[..8]
+    );
+    assert_eq!(promoted.document.path, expected_path);
+    assert_eq!(
+        workspace.read(&expected_path).await.expect("read").content,
+        "# Damages summary"
+    );
+    let stored = db
+        .get_job_artifact(job_id, "summary.md")
+        .await
+        .expect("get artifact")
+        .expect("artifact row");
+    assert_eq!(stored.promoted_to.as_deref(), Some(expected_path.as_str()));
 
-    let err = invoices_void_handler(
+    let err = jobs_artifact_promote_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(invoice_id.clone()),
+        Path((job_id.to_string(), "chart.png".to_string())),
+        Json(PromoteJobArtifactRequest {
+            matter_id: "demo".to_string(),
+        }),
     )
     .await
-    .expect_err("void on paid invoice should fail");
-    assert_eq!(err.0, StatusCode::CONFLICT);
-    assert!(err.1.contains("status 'paid'"));
+    .expect_err("binary artifact");
+    assert_eq!(err.0, StatusCode::UNPROCESSABLE_ENTITY);
 
-    let invoice_after = store
-        .get_invoice(
-            &state.user_id,
-            Uuid::parse_str(&invoice_id).expect("invoice uuid"),
-        )
-        .await
-        .expect("load invoice")
-        .expect("invoice exists");
-    assert_eq!(invoice_after.status, crate::db::InvoiceStatus::Paid);
+    let purged = crate::orchestrator::artifacts::purge_expired_artifacts(
+        db.as_ref(),
+        std::time::Duration::ZERO,
+    )
+    .await
+    .expect("purge");
+    assert_eq!(purged, 2);
+    assert!(!std::path::Path::new(&stored.storage_path).exists());
+    assert!(db.list_job_artifacts(job_id).await.unwrap().is_empty());
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn trust_deposits_concurrently_update_balance_atomically() {
+async fn matter_task_board_groups_moves_and_flags_blocked_tasks() {
+    use crate::db::MatterTaskStatus;
+
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
@@ -4575,143 +10450,155 @@ async fn trust_deposits_concurrently_update_balance_atomically() {
     ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
         .await
         .expect("sync matter row");
-    let store = state.store.as_ref().expect("store should exist").clone();
-    let user_id = state.user_id.clone();
-    let barrier = Arc::new(tokio::sync::Barrier::new(3));
-
-    let barrier_a = Arc::clone(&barrier);
-    let store_a = Arc::clone(&store);
-    let user_a = user_id.clone();
-    let task_a = tokio::spawn(async move {
-        barrier_a.wait().await;
-        crate::legal::billing::record_trust_deposit(
-            store_a.as_ref(),
-            &user_a,
-            "demo",
-            rust_decimal::Decimal::new(5000, 2),
-            "Lead A",
-            "Concurrent deposit A",
-            None,
-        )
-        .await
-    });
 
-    let barrier_b = Arc::clone(&barrier);
-    let store_b = Arc::clone(&store);
-    let user_b = user_id.clone();
-    let task_b = tokio::spawn(async move {
-        barrier_b.wait().await;
-        crate::legal::billing::record_trust_deposit(
-            store_b.as_ref(),
-            &user_b,
-            "demo",
-            rust_decimal::Decimal::new(5000, 2),
-            "Lead B",
-            "Concurrent deposit B",
-            None,
+    let mut ids: Vec<String> = Vec::new();
+    for (title, assignee, blocked_on) in [
+        ("Collect exhibits", Some("pat"), None),
+        ("Draft motion", Some("alex"), None),
+        ("File motion", None, Some(1)),
+    ] {
+        let blocked_by = blocked_on.map(|i| vec![ids[i].clone()]).unwrap_or_default();
+        let (_, Json(task)) = matter_tasks_create_handler(
+            State(Arc::clone(&state)),
+            owner_principal(),
+            Path("demo".to_string()),
+            Json(CreateMatterTaskRequest {
+                title: title.to_string(),
+                description: None,
+                status: None,
+                assignee: assignee.map(str::to_string),
+                due_at: None,
+                blocked_by,
+                parent_task_id: None,
+                checklist: Vec::new(),
+            }),
         )
         .await
-    });
-
-    barrier.wait().await;
-    let entry_a = task_a
-        .await
-        .expect("task A should join")
-        .expect("deposit A should succeed");
-    let entry_b = task_b
-        .await
-        .expect("task B should join")
-        .expect("deposit B should succeed");
+        .expect("create task");
+        ids.push(task.id);
+    }
 
-    let mut balances = vec![entry_a.balance_after, entry_b.balance_after];
-    balances.sort();
+    let Json(board) = matter_tasks_board_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Query(MatterTaskBoardQuery { swimlane: None }),
+    )
+    .await
+    .expect("status board");
+    let lanes: Vec<_> = board.lanes.iter().map(|l| l.key.as_deref()).collect();
     assert_eq!(
-        balances,
-        vec![
-            rust_decimal::Decimal::new(5000, 2),
-            rust_decimal::Decimal::new(10000, 2)
+        lanes,
+        [
+            Some("todo"),
+            Some("in_progress"),
+            Some("blocked"),
+            Some("done"),
+            Some("cancelled")
         ]
     );
-
-    let balance = store
-        .current_trust_balance(&state.user_id, "demo")
-        .await
-        .expect("read balance");
-    assert_eq!(balance, rust_decimal::Decimal::new(10000, 2));
-}
-
-#[cfg(feature = "libsql")]
-#[tokio::test]
-async fn matter_time_create_rejects_non_positive_hours() {
-    let (db, _tmp) = crate::testing::test_db().await;
-    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
-        .await
-        .expect("sync matter row");
-
-    let result = matter_time_create_handler(
-        State(state),
+    let todo = &board.lanes[0].tasks;
+    let positions: Vec<_> = todo.iter().map(|c| c.task.position).collect();
+    assert_eq!(positions, [0, 1, 2], "new tasks append to their lane");
+    assert!(todo[2].is_blocked);
+    assert_eq!(todo[2].open_blockers, vec![ids[1].clone()]);
+    assert_eq!(todo[1].blocks, vec![ids[2].clone()]);
+
+    // Finishing the blocker clears the indicator; the moved card lands in done.
+    let Json(board) = matter_tasks_board_move_handler(
+        State(Arc::clone(&state)),
         owner_principal(),
         Path("demo".to_string()),
-        Json(CreateTimeEntryRequest {
-            timekeeper: "Paralegal".to_string(),
-            description: "Prepare draft".to_string(),
-            hours: "0".to_string(),
-            hourly_rate: Some("200".to_string()),
-            task_code: None,
-            activity_code: None,
-            entry_date: "2026-04-10".to_string(),
-            billable: Some(true),
-            block_billing_flag: None,
-            block_billing_reason: None,
+        Json(MoveMatterTasksRequest {
+            moves: vec![MatterTaskMoveRequest {
+                task_id: ids[1].clone(),
+                status: Some("done".to_string()),
+                assignee: None,
+                position: 0,
+            }],
+            swimlane: None,
         }),
     )
-    .await;
-
-    let err = result.expect_err("zero-hour time entry should be rejected");
-    assert_eq!(err.0, StatusCode::BAD_REQUEST);
-    assert!(err.1.contains("'hours' must be greater than 0"));
-}
+    .await
+    .expect("move task");
+    assert_eq!(board.lanes[3].tasks[0].task.id, ids[1]);
+    let file_card = &board.lanes[0].tasks[1];
+    assert_eq!(file_card.task.id, ids[2]);
+    assert!(!file_card.is_blocked);
+    assert!(file_card.open_blockers.is_empty());
 
-#[cfg(feature = "libsql")]
-#[tokio::test]
-async fn matter_expense_create_rejects_non_positive_amount() {
-    let (db, _tmp) = crate::testing::test_db().await;
-    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+    let Json(board) = matter_tasks_board_reorder_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(ReorderMatterTasksRequest {
+            task_ids: vec![ids[2].clone(), ids[0].clone()],
+            swimlane: Some("assignee".to_string()),
+        }),
+    )
+    .await
+    .expect("reorder tasks");
+    assert_eq!(board.swimlane, "assignee");
+    let lanes: Vec<_> = board.lanes.iter().map(|l| l.key.as_deref()).collect();
+    assert_eq!(lanes, [Some("alex"), Some("pat"), None]);
+    assert_eq!(board.lanes[2].tasks[0].task.id, ids[2]);
+    assert_eq!(board.lanes[2].tasks[0].task.position, 0);
+    assert_eq!(board.lanes[1].tasks[0].task.position, 1);
+
+    // An unknown task rolls back the whole batch.
+    let err = matter_tasks_board_move_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(MoveMatterTasksRequest {
+            moves: vec![
+                MatterTaskMoveRequest {
+                    task_id: ids[0].clone(),
+                    status: Some("cancelled".to_string()),
+                    assignee: Some(None),
+                    position: 5,
+                },
+                MatterTaskMoveRequest {
+                    task_id: Uuid::new_v4().to_string(),
+                    status: None,
+                    assignee: None,
+                    position: 0,
+                },
+            ],
+            swimlane: None,
+        }),
+    )
+    .await
+    .expect_err("unknown task");
+    assert_eq!(err.0, StatusCode::NOT_FOUND);
+    let tasks = db
+        .list_matter_tasks("test-user", "demo")
         .await
-        .expect("sync matter row");
+        .expect("list tasks");
+    let untouched = tasks
+        .iter()
+        .find(|t| t.id.to_string() == ids[0])
+        .expect("task");
+    assert_eq!(untouched.status, MatterTaskStatus::Todo);
+    assert_eq!(untouched.assignee.as_deref(), Some("pat"));
 
-    let result = matter_expenses_create_handler(
+    let err = matter_tasks_board_reorder_handler(
         State(state),
         owner_principal(),
         Path("demo".to_string()),
-        Json(CreateExpenseEntryRequest {
-            submitted_by: "Associate".to_string(),
-            description: "Filing fee".to_string(),
-            amount: "0".to_string(),
-            category: "filing_fee".to_string(),
-            entry_date: "2026-04-10".to_string(),
-            receipt_path: None,
-            billable: Some(true),
+        Json(ReorderMatterTasksRequest {
+            task_ids: vec![ids[0].clone(), ids[0].clone()],
+            swimlane: None,
         }),
     )
-    .await;
-
-    let err = result.expect_err("zero-amount expense entry should be rejected");
+    .await
+    .expect_err("duplicate ids");
     assert_eq!(err.0, StatusCode::BAD_REQUEST);
-    assert!(err.1.contains("'amount' must be greater than 0"));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matter_time_delete_rejects_billed_entry() {
+async fn matter_subtasks_roll_up_to_parent_and_keep_checklist_order() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
@@ -4720,512 +10607,623 @@ async fn matter_time_delete_rejects_billed_entry() {
     ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
         .await
         .expect("sync matter row");
-    let store = state.store.as_ref().expect("store should exist");
 
-    let billed_entry = store
-        .create_time_entry(
-            &state.user_id,
-            "demo",
-            &crate::db::CreateTimeEntryParams {
-                timekeeper: "Lead".to_string(),
-                description: "Billed work".to_string(),
-                hours: rust_decimal::Decimal::new(150, 2),
-                hourly_rate: Some(rust_decimal::Decimal::new(30000, 2)),
-                task_code: None,
-                activity_code: None,
-                resolved_rate: None,
-                rate_source: None,
-                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 4, 9).expect("valid date"),
-                billable: true,
-                block_billing_flag: false,
-                block_billing_reason: None,
-            },
-        )
+    let create = |title: &str, parent: Option<String>, checklist: Vec<&str>| {
+        let state = Arc::clone(&state);
+        let request = CreateMatterTaskRequest {
+            title: title.to_string(),
+            description: None,
+            status: None,
+            assignee: None,
+            due_at: None,
+            blocked_by: Vec::new(),
+            parent_task_id: parent,
+            checklist: checklist
+                .into_iter()
+                .map(|text| crate::db::MatterTaskChecklistItem {
+                    text: text.to_string(),
+                    done: false,
+                })
+                .collect(),
+        };
+        async move {
+            matter_tasks_create_handler(
+                State(state),
+                owner_principal(),
+                Path("demo".to_string()),
+                Json(request),
+            )
+            .await
+            .map(|(_, Json(task))| task)
+        }
+    };
+    let patch = |task_id: &str, status: Option<&str>, parent: Option<Option<String>>| {
+        let state = Arc::clone(&state);
+        let path = ("demo".to_string(), task_id.to_string());
+        let request = UpdateMatterTaskRequest {
+            title: None,
+            description: None,
+            status: status.map(str::to_string),
+            assignee: None,
+            due_at: None,
+            blocked_by: None,
+            parent_task_id: parent,
+            checklist: None,
+        };
+        async move {
+            matter_tasks_patch_handler(State(state), owner_principal(), Path(path), Json(request))
+                .await
+                .map(|Json(task)| task)
+        }
+    };
+
+    let parent = create(
+        "Answer complaint",
+        None,
+        vec!["Calendar due date", " Draft "],
+    )
+    .await
+    .expect("create parent");
+    assert_eq!(parent.checklist.len(), 2);
+    assert_eq!(parent.checklist[1].text, "Draft");
+    let first = create("Draft answer", Some(parent.id.clone()), Vec::new())
         .await
-        .expect("create billed seed entry");
-    let unbilled_entry = store
-        .create_time_entry(
-            &state.user_id,
-            "demo",
-            &crate::db::CreateTimeEntryParams {
-                timekeeper: "Lead".to_string(),
-                description: "Unbilled work".to_string(),
-                hours: rust_decimal::Decimal::new(50, 2),
-                hourly_rate: Some(rust_decimal::Decimal::new(30000, 2)),
-                task_code: None,
-                activity_code: None,
-                resolved_rate: None,
-                rate_source: None,
-                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 4, 10).expect("valid date"),
-                billable: true,
-                block_billing_flag: false,
-                block_billing_reason: None,
-            },
-        )
+        .expect("create subtask");
+    let second = create("Serve answer", Some(parent.id.clone()), Vec::new())
         .await
-        .expect("create unbilled seed entry");
+        .expect("create subtask");
+    assert_eq!(first.parent_task_id.as_deref(), Some(parent.id.as_str()));
 
-    let marked = store
-        .mark_time_entries_billed(&state.user_id, &[billed_entry.id], "inv-1001")
+    let err = create("Orphan", Some(Uuid::new_v4().to_string()), Vec::new())
         .await
-        .expect("mark billed entry");
-    assert_eq!(marked, 1);
+        .expect_err("unknown parent");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    let err = create("Blank step", None, vec!["  "])
+        .await
+        .expect_err("blank checklist item");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    let err = patch(&parent.id, None, Some(Some(first.id.clone())))
+        .await
+        .expect_err("cycle");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
 
-    let billed_after = store
-        .get_time_entry(&state.user_id, "demo", billed_entry.id)
+    let status_of = |id: String| {
+        let db = Arc::clone(&db);
+        async move {
+            db.list_matter_tasks("test-user", "demo")
+                .await
+                .expect("list tasks")
+                .into_iter()
+                .find(|t| t.id.to_string() == id)
+                .map(|t| t.status.as_str().to_string())
+        }
+    };
+
+    patch(&first.id, Some("done"), None)
         .await
-        .expect("load billed entry")
-        .expect("billed entry should exist");
-    let unbilled_after = store
-        .get_time_entry(&state.user_id, "demo", unbilled_entry.id)
+        .expect("finish first");
+    assert_eq!(status_of(parent.id.clone()).await.as_deref(), Some("todo"));
+    patch(&second.id, Some("cancelled"), None)
         .await
-        .expect("load unbilled entry")
-        .expect("unbilled entry should exist");
-    assert_eq!(billed_after.billed_invoice_id.as_deref(), Some("inv-1001"));
-    assert!(unbilled_after.billed_invoice_id.is_none());
+        .expect("cancel second");
+    assert_eq!(status_of(parent.id.clone()).await.as_deref(), Some("done"));
 
-    let billed_delete = matter_time_delete_handler(
+    let status = matter_tasks_delete_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(("demo".to_string(), billed_entry.id.to_string())),
+        Path(("demo".to_string(), parent.id.clone())),
     )
-    .await;
-    let err = billed_delete.expect_err("billed entry should not be deletable");
-    assert_eq!(err.0, StatusCode::CONFLICT);
-    assert!(err.1.contains("billed"));
-
-    let unbilled_delete = matter_time_delete_handler(
-        State(state),
+    .await
+    .expect("delete parent");
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let Json(tasks) = matter_tasks_list_handler(
+        State(Arc::clone(&state)),
         owner_principal(),
-        Path(("demo".to_string(), unbilled_entry.id.to_string())),
+        Path("demo".into()),
     )
     .await
-    .expect("unbilled entry should be deletable");
-    assert_eq!(unbilled_delete, StatusCode::NO_CONTENT);
+    .expect("list tasks");
+    assert_eq!(tasks.tasks.len(), 2);
+    assert!(tasks.tasks.iter().all(|t| t.parent_task_id.is_none()));
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matter_time_summary_aggregates_hours_and_expenses() {
+async fn global_search_ranks_hits_across_matter_records_and_documents() {
+    use crate::db::{
+        CreateMatterNoteParams, CreateMatterTaskParams, CreateTimeEntryParams, MatterTaskStatus,
+    };
+
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
-        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    let state = test_gateway_state_with_store_workspace_and_legal(
+        Arc::clone(&db),
+        Arc::clone(&workspace),
+        test_legal_config(),
+    );
     ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
         .await
         .expect("sync matter row");
-    let store = state.store.as_ref().expect("store should exist");
 
-    let time_one = store
-        .create_time_entry(
-            &state.user_id,
-            "demo",
-            &crate::db::CreateTimeEntryParams {
-                timekeeper: "Lead".to_string(),
-                description: "Billable review".to_string(),
-                hours: rust_decimal::Decimal::new(150, 2),
-                hourly_rate: Some(rust_decimal::Decimal::new(35000, 2)),
-                task_code: None,
-                activity_code: None,
-                resolved_rate: None,
-                rate_source: None,
-                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 4, 11).expect("valid date"),
-                billable: true,
-                block_billing_flag: false,
-                block_billing_reason: None,
-            },
-        )
-        .await
-        .expect("create first time entry");
-    store
-        .create_time_entry(
-            &state.user_id,
-            "demo",
-            &crate::db::CreateTimeEntryParams {
-                timekeeper: "Paralegal".to_string(),
-                description: "Internal prep".to_string(),
-                hours: rust_decimal::Decimal::new(50, 2),
-                hourly_rate: None,
-                task_code: None,
-                activity_code: None,
-                resolved_rate: None,
-                rate_source: None,
-                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 4, 11).expect("valid date"),
-                billable: false,
-                block_billing_flag: false,
-                block_billing_reason: None,
-            },
-        )
-        .await
-        .expect("create second time entry");
-    let expense_one = store
-        .create_expense_entry(
-            &state.user_id,
-            "demo",
-            &crate::db::CreateExpenseEntryParams {
-                submitted_by: "Lead".to_string(),
-                description: "Court filing fee".to_string(),
-                amount: rust_decimal::Decimal::new(10000, 2),
-                category: crate::db::ExpenseCategory::FilingFee,
-                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 4, 11).expect("valid date"),
-                receipt_path: None,
-                billable: true,
-            },
-        )
-        .await
-        .expect("create first expense entry");
-    store
-        .create_expense_entry(
-            &state.user_id,
-            "demo",
-            &crate::db::CreateExpenseEntryParams {
-                submitted_by: "Lead".to_string(),
-                description: "Internal courier".to_string(),
-                amount: rust_decimal::Decimal::new(4000, 2),
-                category: crate::db::ExpenseCategory::Other,
-                entry_date: chrono::NaiveDate::from_ymd_opt(2026, 4, 11).expect("valid date"),
-                receipt_path: None,
-                billable: false,
-            },
+    db.create_matter_task(
+        "test-user",
+        "demo",
+        &CreateMatterTaskParams {
+            title: "Review Henderson easement survey".to_string(),
+            description: None,
+            status: MatterTaskStatus::Todo,
+            assignee: None,
+            due_at: None,
+            blocked_by: Vec::new(),
+            parent_task_id: None,
+            checklist: Vec::new(),
+        },
+    )
+    .await
+    .expect("create task");
+    db.create_matter_note(
+        "test-user",
+        "demo",
+        &CreateMatterNoteParams {
+            author: "Lead".to_string(),
+            body: "Client forwarded an email about the Henderson easement".to_string(),
+            pinned: false,
+            parent_id: None,
+        },
+    )
+    .await
+    .expect("create note");
+    db.create_time_entry(
+        "test-user",
+        "demo",
+        &CreateTimeEntryParams {
+            timekeeper: "ab".to_string(),
+            description: "Call with Henderson counsel re easement".to_string(),
+            hours: rust_decimal::Decimal::new(5, 1),
+            hourly_rate: None,
+            task_code: None,
+            activity_code: None,
+            resolved_rate: None,
+            rate_source: None,
+            entry_date: Utc::now().date_naive(),
+            billable: true,
+            block_billing_flag: false,
+            block_billing_reason: None,
+        },
+    )
+    .await
+    .expect("create time entry");
+    workspace
+        .write(
+            "matters/demo/communications/henderson.md",
+            "Email from Henderson: the easement runs along the north boundary.",
         )
         .await
-        .expect("create second expense entry");
-    store
-        .mark_time_entries_billed(&state.user_id, &[time_one.id], "inv-2001")
-        .await
-        .expect("mark one time entry billed");
-    store
-        .mark_expense_entries_billed(&state.user_id, &[expense_one.id], "inv-2001")
-        .await
-        .expect("mark one expense entry billed");
+        .expect("seed document");
 
-    let Json(summary) =
-        matter_time_summary_handler(State(state), owner_principal(), Path("demo".to_string()))
-            .await
-            .expect("summary handler should succeed");
+    let search = |q: &str, types: Option<&str>, principal| {
+        let state = Arc::clone(&state);
+        let query = GlobalSearchQuery {
+            q: q.to_string(),
+            types: types.map(str::to_string),
+            limit: None,
+        };
+        async move { global_search_handler(State(state), principal, Query(query)).await }
+    };
+
+    let Json(all) = search(
+        "that email about the Henderson easement",
+        None,
+        owner_principal(),
+    )
+    .await
+    .expect("search");
+    for kind in ["task", "note", "time_entry", "document"] {
+        assert_eq!(all.facets.get(kind), Some(&1), "missing {kind} hit");
+    }
+    assert_eq!(all.total, all.results.len());
+    assert!(
+        all.results
+            .windows(2)
+            .all(|pair| pair[0].score >= pair[1].score)
+    );
+    let document = all
+        .results
+        .iter()
+        .find(|hit| hit.kind == "document")
+        .expect("document hit");
+    assert_eq!(document.matter_id.as_deref(), Some("demo"));
+    assert_eq!(
+        document.path.as_deref(),
+        Some("matters/demo/communications/henderson.md")
+    );
+    assert!(
+        all.results
+            .iter()
+            .all(|hit| hit.matter_id.as_deref() == Some("demo"))
+    );
 
-    let total_hours = summary
-        .total_hours
-        .parse::<rust_decimal::Decimal>()
-        .expect("parse total hours");
-    let billable_hours = summary
-        .billable_hours
-        .parse::<rust_decimal::Decimal>()
-        .expect("parse billable hours");
-    let unbilled_hours = summary
-        .unbilled_hours
-        .parse::<rust_decimal::Decimal>()
-        .expect("parse unbilled hours");
-    let total_expenses = summary
-        .total_expenses
-        .parse::<rust_decimal::Decimal>()
-        .expect("parse total expenses");
-    let billable_expenses = summary
-        .billable_expenses
-        .parse::<rust_decimal::Decimal>()
-        .expect("parse billable expenses");
-    let unbilled_expenses = summary
-        .unbilled_expenses
-        .parse::<rust_decimal::Decimal>()
-        .expect("parse unbilled expenses");
+    let Json(tasks) = search("henderson", Some("task"), owner_principal())
+        .await
+        .expect("filtered search");
+    assert_eq!(tasks.results.len(), 1);
+    assert_eq!(tasks.results[0].kind, "task");
 
-    assert_eq!(total_hours, rust_decimal::Decimal::new(200, 2));
-    assert_eq!(billable_hours, rust_decimal::Decimal::new(150, 2));
-    assert_eq!(unbilled_hours, rust_decimal::Decimal::new(50, 2));
-    assert_eq!(total_expenses, rust_decimal::Decimal::new(14000, 2));
-    assert_eq!(billable_expenses, rust_decimal::Decimal::new(10000, 2));
-    assert_eq!(unbilled_expenses, rust_decimal::Decimal::new(4000, 2));
+    let err = search("the", None, owner_principal())
+        .await
+        .expect_err("stop words only");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    let err = search("henderson", Some("emails"), owner_principal())
+        .await
+        .expect_err("unknown type");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    let err = search(
+        "henderson",
+        None,
+        principal_with_role("associate", UserRole::Attorney),
+    )
+    .await
+    .expect_err("non-owner");
+    assert_eq!(err.0, StatusCode::FORBIDDEN);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn billing_rates_apply_matter_override_and_export_ledes() {
+async fn memory_git_sync_attributes_api_writes_to_the_user() {
+    if std::process::Command::new("git")
+        .arg("--version")
+        .output()
+        .is_err()
+    {
+        return;
+    }
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    seed_valid_matter(workspace.as_ref(), "demo").await;
-    let state =
+    let mut state =
         test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
-        .await
-        .expect("sync matter row");
-
-    let (_status, Json(default_rate)) = billing_rates_create_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Json(CreateBillingRateScheduleRequest {
-            matter_id: None,
-            timekeeper: "Lead".to_string(),
-            rate: "425.00".to_string(),
-            effective_start: "2026-01-01".to_string(),
-            effective_end: None,
-        }),
+    let repo_dir = tempfile::tempdir().expect("repo dir");
+    let mirror = Arc::new(crate::workspace::git_mirror::GitMirror::new(
+        repo_dir.path().join("workspace.git"),
+        None,
+        "main".to_string(),
+    ));
+    Arc::get_mut(&mut state).expect("fresh state").git_mirror = Some(Arc::clone(&mirror));
+    db.set_setting(
+        "test-user",
+        MATTER_ACTIVE_SETTING,
+        &serde_json::Value::String("demo".to_string()),
     )
     .await
-    .expect("create timekeeper default rate");
-    assert_eq!(default_rate.rate, "425.00");
+    .expect("set active matter");
 
-    let (_status, Json(matter_rate)) = billing_rates_create_handler(
+    let _ = memory_write_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(CreateBillingRateScheduleRequest {
-            matter_id: Some("demo".to_string()),
-            timekeeper: "Lead".to_string(),
-            rate: "375.00".to_string(),
-            effective_start: "2026-01-01".to_string(),
-            effective_end: None,
+        HeaderMap::new(),
+        Json(MemoryWriteRequest {
+            path: "notes.md".to_string(),
+            content: "Client call notes".to_string(),
         }),
     )
     .await
-    .expect("create matter override rate");
-    assert_eq!(matter_rate.matter_id.as_deref(), Some("demo"));
+    .expect("user write");
+    workspace
+        .write("MEMORY.md", "Overnight summary")
+        .await
+        .expect("agent write");
 
-    let (_status, Json(entry)) = matter_time_create_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-        Json(CreateTimeEntryRequest {
-            timekeeper: "Lead".to_string(),
-            description: "Draft motion for summary judgment".to_string(),
-            hours: "1.50".to_string(),
-            hourly_rate: None,
-            task_code: Some("b110".to_string()),
-            activity_code: Some("a101".to_string()),
-            entry_date: "2026-04-10".to_string(),
-            billable: Some(true),
-            block_billing_flag: None,
-            block_billing_reason: None,
-        }),
-    )
-    .await
-    .expect("create rate-managed time entry");
-    assert_eq!(entry.rate_source.as_deref(), Some("matter_override"));
+    let Json(status) = memory_git_status_handler(State(Arc::clone(&state)), owner_principal())
+        .await
+        .expect("status");
+    assert_eq!(status.pending_user_changes, 1);
+    assert!(status.head.is_none());
+
+    let Json(synced) = memory_git_sync_handler(State(Arc::clone(&state)), owner_principal())
+        .await
+        .expect("sync");
+    let authors: Vec<_> = synced
+        .commits
+        .iter()
+        .map(|c| (c.author.as_str(), c.paths.clone()))
+        .collect();
     assert_eq!(
-        entry
-            .resolved_rate
-            .as_deref()
-            .expect("resolved rate should exist")
-            .parse::<rust_decimal::Decimal>()
-            .expect("parse resolved rate"),
-        rust_decimal::Decimal::new(37500, 2)
+        authors,
+        vec![
+            ("user test-user", vec!["matters/demo/notes.md".to_string()]),
+            ("agent", vec!["MEMORY.md".to_string()]),
+        ]
     );
+    assert_eq!(synced.head, mirror.head().await.unwrap());
 
-    let (_status, Json(invoice)) = invoices_save_handler(
+    let err = memory_git_import_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(DraftInvoiceRequest {
-            matter_id: "demo".to_string(),
-            invoice_number: "INV-LEDES-100".to_string(),
-            due_date: Some("2026-05-10".to_string()),
-            notes: None,
+        Json(MemoryGitImportRequest {
+            rev: Some("--upload-pack=evil".to_string()),
         }),
     )
     .await
-    .expect("draft invoice should succeed");
-    let _ = invoices_finalize_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path(invoice.invoice.id.clone()),
-    )
-    .await
-    .expect("finalize invoice should succeed");
+    .expect_err("option-like revision");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
 
-    let (status, Json(ledes)) = invoice_ledes_handler(
-        State(Arc::clone(&state)),
-        owner_principal(),
-        Path(invoice.invoice.id.clone()),
-        Query(InvoiceLedesQuery {
-            format: Some("98b".to_string()),
-        }),
+    let err = memory_git_sync_handler(
+        State(state),
+        principal_with_role("associate", UserRole::Attorney),
     )
     .await
-    .expect("LEDES export should succeed");
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(ledes.format, "98b");
-    let content = ledes.content.expect("LEDES content should exist");
-    assert!(content.starts_with("LEDES1998B[]\n"));
-    assert!(content.contains("|B110||A101|"));
-    assert!(content.contains("|375.00|Lead|OT|"));
-    assert!(ledes.errors.is_empty());
+    .expect_err("non-admin sync");
+    assert_eq!(err.0, StatusCode::FORBIDDEN);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn billing_rates_enforce_rbac_and_overlap_validation() {
+async fn template_variable_schema_guards_document_generation() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
-    seed_valid_matter(workspace.as_ref(), "other").await;
     let state =
         test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
     ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
         .await
-        .expect("sync demo matter row");
-    ensure_matter_db_row_from_workspace(state.as_ref(), "other")
-        .await
-        .expect("sync other matter row");
-
-    let staff_principal = principal_with_role("staff-user", UserRole::Staff);
-    let Json(list) = billing_rates_list_handler(State(Arc::clone(&state)), staff_principal.clone())
-        .await
-        .expect("authenticated staff should list rates");
-    assert!(list.schedules.is_empty());
+        .expect("sync matter row");
+    let Json(templates_resp) = matter_templates_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+    )
+    .await
+    .expect("templates");
+    let template_id = templates_resp
+        .templates
+        .iter()
+        .find(|template| template.name == "chronology.md")
+        .and_then(|template| template.id.clone())
+        .expect("template id");
 
-    let create_forbidden = billing_rates_create_handler(
+    let err = template_variables_update_handler(
         State(Arc::clone(&state)),
-        staff_principal.clone(),
-        Json(CreateBillingRateScheduleRequest {
-            matter_id: None,
-            timekeeper: "Lead".to_string(),
-            rate: "425.00".to_string(),
-            effective_start: "2026-01-01".to_string(),
-            effective_end: Some("2026-01-31".to_string()),
+        owner_principal(),
+        Path(template_id.clone()),
+        Json(UpdateTemplateVariablesRequest {
+            variables: serde_json::json!([{"name": "court", "type": "enum"}]),
         }),
     )
     .await
-    .expect_err("non-admin create should be forbidden");
-    assert_eq!(create_forbidden.0, StatusCode::FORBIDDEN);
+    .expect_err("enum without choices");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
 
-    let (_status, Json(january_rate)) = billing_rates_create_handler(
+    let _ = template_variables_update_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(CreateBillingRateScheduleRequest {
-            matter_id: None,
-            timekeeper: "Lead".to_string(),
-            rate: "425.00".to_string(),
-            effective_start: "2026-01-01".to_string(),
-            effective_end: Some("2026-01-31".to_string()),
+        Path(template_id.clone()),
+        Json(UpdateTemplateVariablesRequest {
+            variables: serde_json::json!([
+                {"name": "court", "type": "enum", "required": true, "choices": ["SDNY", "EDNY"]},
+                {"name": "hearing_date", "type": "date", "description": "Date of the hearing"},
+            ]),
         }),
     )
     .await
-    .expect("admin should create initial schedule");
+    .expect("set schema");
 
-    let invalid_range = billing_rates_create_handler(
+    let generate = |extra: serde_json::Value| {
+        documents_generate_handler(
+            State(Arc::clone(&state)),
+            owner_principal(),
+            Json(GenerateDocumentRequest {
+                template_id: template_id.clone(),
+                matter_id: "demo".to_string(),
+                extra,
+                display_name: None,
+                category: None,
+                label: None,
+                locale: None,
+            }),
+        )
+    };
+    let err = generate(serde_json::json!({"hearing_date": "2026-03-02"}))
+        .await
+        .expect_err("missing required variable");
+    assert_eq!(err.0, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(err.1.contains("'court' is required"), "{}", err.1);
+    let (status, _) = generate(serde_json::json!({"court": "SDNY"}))
+        .await
+        .expect("valid variables");
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Generation re-syncs templates from the workspace; the schema survives.
+    let Json(schema) = template_variables_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(CreateBillingRateScheduleRequest {
-            matter_id: None,
-            timekeeper: "Lead".to_string(),
-            rate: "425.00".to_string(),
-            effective_start: "2026-03-01".to_string(),
-            effective_end: Some("2026-02-28".to_string()),
-        }),
+        Path(template_id),
     )
     .await
-    .expect_err("invalid date range should be rejected");
-    assert_eq!(invalid_range.0, StatusCode::BAD_REQUEST);
+    .expect("read schema");
+    assert_eq!(schema.name, "chronology.md");
+    let names: Vec<_> = schema.variables.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(names, vec!["court", "hearing_date"]);
+    assert!(schema.variables[0].required);
+}
 
-    let overlap = billing_rates_create_handler(
+#[tokio::test]
+async fn shared_template_library_versions_promotions_and_usage() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+        .await
+        .expect("sync matter row");
+    let Json(templates_resp) = matter_templates_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(CreateBillingRateScheduleRequest {
-            matter_id: None,
-            timekeeper: "Lead".to_string(),
-            rate: "430.00".to_string(),
-            effective_start: "2026-01-31".to_string(),
-            effective_end: Some("2026-02-15".to_string()),
-        }),
+        Path("demo".to_string()),
     )
     .await
-    .expect_err("inclusive date overlap should be rejected");
-    assert_eq!(overlap.0, StatusCode::CONFLICT);
+    .expect("templates");
+    let matter_template_id = templates_resp
+        .templates
+        .iter()
+        .find(|template| template.name == "chronology.md")
+        .and_then(|template| template.id.clone())
+        .expect("template id");
 
-    let (_status, Json(february_rate)) = billing_rates_create_handler(
+    let promote_request = || PromoteTemplateRequest {
+        name: Some("Chronology".to_string()),
+        practice_area: Some("Litigation".to_string()),
+        jurisdiction: Some("NY".to_string()),
+        description: None,
+    };
+    let err = template_promote_handler(
+        State(Arc::clone(&state)),
+        principal_with_role("associate", UserRole::Attorney),
+        Path(matter_template_id.clone()),
+        Json(promote_request()),
+    )
+    .await
+    .expect_err("only the owner curates the library");
+    assert_eq!(err.0, StatusCode::FORBIDDEN);
+    let (status, Json(promoted)) = template_promote_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(CreateBillingRateScheduleRequest {
-            matter_id: None,
-            timekeeper: "Lead".to_string(),
-            rate: "430.00".to_string(),
-            effective_start: "2026-02-01".to_string(),
-            effective_end: None,
-        }),
+        Path(matter_template_id.clone()),
+        Json(promote_request()),
     )
     .await
-    .expect("adjacent non-overlapping range should succeed");
+    .expect("promote");
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(promoted.version, 1);
+    assert_eq!(
+        promoted.template.promoted_from.as_deref(),
+        Some(matter_template_id.as_str())
+    );
+    let shared_id = promoted.template.id.clone();
+    let original_body = promoted.body.clone();
 
-    let (_status, Json(other_matter_rate)) = billing_rates_create_handler(
+    let err = shared_templates_create_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(CreateBillingRateScheduleRequest {
-            matter_id: Some("other".to_string()),
-            timekeeper: "Lead".to_string(),
-            rate: "390.00".to_string(),
-            effective_start: "2026-01-15".to_string(),
-            effective_end: None,
+        Json(CreateSharedTemplateRequest {
+            name: "Chronology".to_string(),
+            body: "duplicate".to_string(),
+            variables: None,
+            practice_area: None,
+            jurisdiction: None,
+            description: None,
         }),
     )
     .await
-    .expect("same timekeeper on different matter scope should succeed");
-    assert_eq!(other_matter_rate.matter_id.as_deref(), Some("other"));
+    .expect_err("duplicate shared name");
+    assert_eq!(err.0, StatusCode::CONFLICT);
 
-    let (_status, Json(other_timekeeper_rate)) = billing_rates_create_handler(
+    let list = |practice_area: Option<&str>, jurisdiction: Option<&str>| {
+        shared_templates_handler(
+            State(Arc::clone(&state)),
+            Query(SharedTemplatesQuery {
+                practice_area: practice_area.map(str::to_string),
+                jurisdiction: jurisdiction.map(str::to_string),
+            }),
+        )
+    };
+    let Json(litigation) = list(Some("litigation"), None).await.expect("list");
+    assert_eq!(litigation.templates.len(), 1);
+    assert_eq!(litigation.templates[0].jurisdiction.as_deref(), Some("NY"));
+    let Json(california) = list(None, Some("CA")).await.expect("list");
+    assert!(california.templates.is_empty());
+
+    let update = |body: &str| {
+        shared_template_update_handler(
+            State(Arc::clone(&state)),
+            owner_principal(),
+            Path(shared_id.clone()),
+            Json(UpdateSharedTemplateRequest {
+                name: None,
+                body: Some(body.to_string()),
+                variables: None,
+                practice_area: None,
+                jurisdiction: Some(String::new()),
+                description: None,
+            }),
+        )
+    };
+    let Json(edited) = update("# Chronology for {{ matter.matter_id }}")
+        .await
+        .expect("edit");
+    assert_eq!(edited.version, 2);
+    assert_eq!(edited.template.jurisdiction, None);
+    assert_eq!(edited.template.practice_area.as_deref(), Some("Litigation"));
+    let Json(unchanged) = update("# Chronology for {{ matter.matter_id }}")
+        .await
+        .expect("no-op edit");
+    assert_eq!(unchanged.version, 2);
+
+    let Json(history) = shared_template_versions_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(CreateBillingRateScheduleRequest {
-            matter_id: None,
-            timekeeper: "Associate".to_string(),
-            rate: "275.00".to_string(),
-            effective_start: "2026-01-15".to_string(),
-            effective_end: None,
-        }),
+        Path(shared_id.clone()),
     )
     .await
-    .expect("different timekeeper should not conflict");
-    assert_eq!(other_timekeeper_rate.timekeeper, "Associate");
-
-    let patch_forbidden = billing_rates_patch_handler(
+    .expect("versions");
+    let versions: Vec<_> = history.versions.iter().map(|v| v.version).collect();
+    assert_eq!(versions, vec![2, 1]);
+    let Json(restored) = shared_template_restore_handler(
         State(Arc::clone(&state)),
-        staff_principal,
-        Path(february_rate.id.clone()),
-        Json(UpdateBillingRateScheduleRequest {
-            matter_id: None,
-            timekeeper: None,
-            rate: Some("440.00".to_string()),
-            effective_start: None,
-            effective_end: None,
-        }),
+        owner_principal(),
+        Path((shared_id.clone(), 1)),
     )
     .await
-    .expect_err("non-admin patch should be forbidden");
-    assert_eq!(patch_forbidden.0, StatusCode::FORBIDDEN);
+    .expect("restore");
+    assert_eq!(restored.version, 3);
+    assert_eq!(restored.body, original_body);
 
-    let patch_overlap = billing_rates_patch_handler(
+    for _ in 0..2 {
+        let _ = documents_generate_handler(
+            State(Arc::clone(&state)),
+            owner_principal(),
+            Json(GenerateDocumentRequest {
+                template_id: shared_id.clone(),
+                matter_id: "demo".to_string(),
+                extra: serde_json::json!({}),
+                display_name: None,
+                category: None,
+                label: None,
+                locale: None,
+            }),
+        )
+        .await
+        .expect("generate from shared template");
+    }
+    let Json(analytics) = template_analytics_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(february_rate.id.clone()),
-        Json(UpdateBillingRateScheduleRequest {
-            matter_id: None,
-            timekeeper: None,
-            rate: None,
-            effective_start: Some("2026-01-15".to_string()),
-            effective_end: None,
-        }),
+        Query(TemplateAnalyticsQuery { since: None }),
     )
     .await
-    .expect_err("patch creating overlap should be rejected");
-    assert_eq!(patch_overlap.0, StatusCode::CONFLICT);
+    .expect("analytics");
+    assert_eq!(analytics.total_uses, 2);
+    assert_eq!(analytics.templates[0].template_id, shared_id);
+    assert_eq!(analytics.templates[0].matters, 1);
+    assert_eq!(
+        analytics.templates[0].practice_area.as_deref(),
+        Some("Litigation")
+    );
 
-    let Json(updated) = billing_rates_patch_handler(
+    let status = shared_template_delete_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(january_rate.id.clone()),
-        Json(UpdateBillingRateScheduleRequest {
-            matter_id: None,
-            timekeeper: None,
-            rate: Some("410.00".to_string()),
-            effective_start: None,
-            effective_end: None,
-        }),
+        Path(shared_id),
     )
     .await
-    .expect("admin patch should succeed");
-    assert_eq!(updated.rate, "410.00");
+    .expect("delete");
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let Json(remaining) = list(None, None).await.expect("list");
+    assert!(remaining.templates.is_empty());
 }
 
-#[cfg(feature = "libsql")]
 #[tokio::test]
-async fn invoice_draft_reports_rate_resolution_and_fallbacks() {
+async fn document_caption_formats_matter_parties_for_the_court() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
@@ -5234,573 +11232,1073 @@ async fn invoice_draft_reports_rate_resolution_and_fallbacks() {
     ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
         .await
         .expect("sync matter row");
+    db.seed_matter_parties("demo", "Demo Client", &["Example Co".to_string()], None)
+        .await
+        .expect("seed parties");
+
+    let request = |client_position: Option<&str>, served: bool| DocumentCaptionRequest {
+        matter_id: "demo".to_string(),
+        title: "Motion to Dismiss".to_string(),
+        jurisdiction: Some("SDNY".to_string()),
+        court: None,
+        case_number: Some("1:26-cv-00042".to_string()),
+        client_position: client_position.map(str::to_string),
+        plaintiffs: None,
+        defendants: None,
+        attorney: Some(crate::legal::caption::CaptionSigner {
+            name: "Lead Counsel".to_string(),
+            bar_number: Some("LC0001".to_string()),
+            ..Default::default()
+        }),
+        served: if served {
+            vec![crate::legal::caption::ServiceEntry {
+                name: "Example Co".to_string(),
+                method: "CM/ECF".to_string(),
+                address: None,
+            }]
+        } else {
+            Vec::new()
+        },
+        date: Some("2026-03-02".to_string()),
+    };
 
-    let (_status, Json(_default_rate)) = billing_rates_create_handler(
+    let Json(caption) = document_caption_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(CreateBillingRateScheduleRequest {
-            matter_id: None,
-            timekeeper: "Lead".to_string(),
-            rate: "425.00".to_string(),
-            effective_start: "2026-01-01".to_string(),
-            effective_end: None,
-        }),
+        Json(request(Some("defendant"), true)),
     )
     .await
-    .expect("create default rate");
-    let (_status, Json(matter_rate)) = billing_rates_create_handler(
+    .expect("caption");
+    assert_eq!(caption.style, "us_federal");
+    assert_eq!(caption.plaintiffs, vec!["Example Co"]);
+    assert_eq!(caption.defendants, vec!["Demo Client"]);
+    assert!(
+        caption
+            .caption
+            .starts_with("UNITED STATES DISTRICT COURT\nSOUTHERN DISTRICT OF NEW YORK")
+    );
+    assert!(caption.caption.contains("Case No. 1:26-cv-00042"));
+    assert!(
+        caption
+            .signature_block
+            .as_deref()
+            .is_some_and(|block| block.ends_with("Attorneys for Defendant Demo Client"))
+    );
+    assert!(
+        caption
+            .certificate_of_service
+            .as_deref()
+            .is_some_and(|cert| cert.contains("- Example Co (by CM/ECF)"))
+    );
+
+    let mut missing_signer = request(None, true);
+    missing_signer.attorney = None;
+    let err = document_caption_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(CreateBillingRateScheduleRequest {
-            matter_id: Some("demo".to_string()),
-            timekeeper: "Lead".to_string(),
-            rate: "375.00".to_string(),
-            effective_start: "2026-01-01".to_string(),
-            effective_end: None,
-        }),
+        Json(missing_signer),
     )
     .await
-    .expect("create matter override rate");
+    .expect_err("certificate needs a signer");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
 
-    let _ = matter_time_create_handler(
+    let err = document_caption_handler(
         State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-        Json(CreateTimeEntryRequest {
-            timekeeper: "Lead".to_string(),
-            description: "Draft complaint".to_string(),
-            hours: "1.00".to_string(),
-            hourly_rate: None,
-            task_code: Some("b110".to_string()),
-            activity_code: Some("a101".to_string()),
-            entry_date: "2026-04-10".to_string(),
-            billable: Some(true),
-            block_billing_flag: None,
-            block_billing_reason: None,
-        }),
+        principal_with_role("outsider", UserRole::Attorney),
+        Json(request(None, false)),
     )
     .await
-    .expect("create matter-override time entry");
-    let _ = matter_time_create_handler(
+    .expect_err("matter access required");
+    assert_eq!(err.0, StatusCode::FORBIDDEN);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn notifications_inbox_pushes_counts_and_snoozes() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("inbox-user", Arc::clone(&db)));
+    let state = test_gateway_state_for_user(Arc::clone(&db), workspace, "inbox-user");
+    let inbox_principal = || principal_with_role("inbox-user", UserRole::Admin);
+    state.spawn_event_subscribers();
+    let mut sse = Box::pin(state.sse.subscribe_raw().expect("sse subscription"));
+
+    let posted = |kind, title: &str| crate::db::NewNotificationParams {
+        user_id: "inbox-user".to_string(),
+        kind,
+        severity: crate::db::AuditSeverity::Info,
+        title: title.to_string(),
+        body: "Three filings due this week.".to_string(),
+        matter_id: Some("demo".to_string()),
+        source: None,
+    };
+    let digest = crate::notifications::notify(
+        db.as_ref(),
+        posted(crate::db::NotificationKind::Routine, "Morning digest"),
+    )
+    .await
+    .expect("notification stored");
+    crate::notifications::notify(
+        db.as_ref(),
+        posted(crate::db::NotificationKind::Deadline, "Answer due Friday"),
+    )
+    .await
+    .expect("notification stored");
+
+    use futures::StreamExt;
+    let event = tokio::time::timeout(std::time::Duration::from_secs(2), sse.next())
+        .await
+        .expect("notification should be pushed")
+        .expect("sse stream open");
+    match event {
+        SseEvent::Notification(info) => {
+            assert_eq!(info.id, digest.id.to_string());
+            assert_eq!(info.kind, "routine");
+        }
+        other => panic!("unexpected SSE event: {other:?}"),
+    }
+
+    let list = |status: Option<&str>| NotificationListQuery {
+        status: status.map(str::to_string),
+        kind: None,
+        limit: None,
+    };
+    let Json(inbox) = notifications_list_handler(
         State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-        Json(CreateTimeEntryRequest {
-            timekeeper: "Associate".to_string(),
-            description: "Review production".to_string(),
-            hours: "2.00".to_string(),
-            hourly_rate: Some("250.00".to_string()),
-            task_code: Some("b120".to_string()),
-            activity_code: Some("a102".to_string()),
-            entry_date: "2026-04-10".to_string(),
-            billable: Some(true),
-            block_billing_flag: None,
-            block_billing_reason: None,
-        }),
+        inbox_principal(),
+        Query(list(None)),
     )
     .await
-    .expect("create manual-rate time entry");
-    let _ = matter_time_create_handler(
+    .expect("list notifications");
+    assert_eq!(inbox.notifications.len(), 2);
+    assert_eq!(inbox.notifications[0].title, "Answer due Friday");
+    assert_eq!(inbox.unread_count, 2);
+    assert_eq!(inbox.unread_by_kind.get("deadline"), Some(&1));
+
+    let Json(read) = notification_read_handler(
         State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".to_string()),
-        Json(CreateTimeEntryRequest {
-            timekeeper: "Paralegal".to_string(),
-            description: "Prepare binders".to_string(),
-            hours: "0.50".to_string(),
-            hourly_rate: None,
-            task_code: Some("b130".to_string()),
-            activity_code: Some("a103".to_string()),
-            entry_date: "2026-04-10".to_string(),
-            billable: Some(true),
-            block_billing_flag: None,
-            block_billing_reason: None,
-        }),
+        inbox_principal(),
+        Path(digest.id.to_string()),
     )
     .await
-    .expect("create no-rate time entry");
+    .expect("mark read");
+    assert!(read.read);
 
-    let Json(draft) = invoices_draft_handler(
+    let deadline_id = inbox.notifications[0].id.clone();
+    let Json(snoozed) = notification_snooze_handler(
         State(Arc::clone(&state)),
-        owner_principal(),
-        Json(DraftInvoiceRequest {
-            matter_id: "demo".to_string(),
-            invoice_number: "INV-DRAFT-200".to_string(),
-            due_date: Some("2026-05-10".to_string()),
-            notes: None,
+        inbox_principal(),
+        Path(deadline_id.clone()),
+        Json(NotificationSnoozeRequest {
+            minutes: Some(60),
+            until: None,
         }),
     )
     .await
-    .expect("draft invoice should succeed");
+    .expect("snooze");
+    assert!(snoozed.snoozed_until.is_some());
 
-    let matter_item = draft
-        .line_items
-        .iter()
-        .find(|item| item.timekeeper.as_deref() == Some("Lead"))
-        .expect("matter override line item");
-    let matter_resolution = matter_item
-        .rate_resolution
-        .as_ref()
-        .expect("matter override should include rate resolution");
-    assert_eq!(matter_item.rate_source.as_deref(), Some("matter_override"));
-    assert!(!matter_resolution.fallback_applied);
-    assert_eq!(matter_resolution.fallback_reason, None);
-    assert_eq!(
-        matter_resolution
-            .matched_schedule
-            .as_ref()
-            .expect("matched schedule should exist")
-            .id,
-        matter_rate.id
-    );
-    assert_eq!(
-        matter_resolution
-            .matched_schedule
-            .as_ref()
-            .and_then(|schedule| schedule.matter_id.as_deref()),
-        Some("demo")
-    );
+    let Json(inbox) = notifications_list_handler(
+        State(Arc::clone(&state)),
+        inbox_principal(),
+        Query(list(None)),
+    )
+    .await
+    .expect("list notifications");
+    assert_eq!(inbox.unread_count, 0);
+    assert_eq!(inbox.notifications.len(), 1);
+    let Json(snoozed_list) = notifications_list_handler(
+        State(Arc::clone(&state)),
+        inbox_principal(),
+        Query(list(Some("snoozed"))),
+    )
+    .await
+    .expect("list snoozed");
+    assert_eq!(snoozed_list.notifications[0].id, deadline_id);
 
-    let manual_item = draft
-        .line_items
-        .iter()
-        .find(|item| item.timekeeper.as_deref() == Some("Associate"))
-        .expect("manual fallback line item");
-    let manual_resolution = manual_item
-        .rate_resolution
-        .as_ref()
-        .expect("manual fallback should include rate resolution");
-    assert_eq!(manual_item.rate_source.as_deref(), Some("manual_override"));
-    assert_eq!(manual_item.resolved_rate.as_deref(), Some("250.00"));
-    assert!(manual_resolution.fallback_applied);
-    assert_eq!(
-        manual_resolution.fallback_reason.as_deref(),
-        Some("manual_override")
-    );
-    assert!(manual_resolution.matched_schedule.is_none());
+    let Json(acked) = notification_ack_handler(
+        State(Arc::clone(&state)),
+        inbox_principal(),
+        Path(deadline_id.clone()),
+    )
+    .await
+    .expect("ack");
+    assert!(acked.acked && acked.snoozed_until.is_none());
+    let err = notification_snooze_handler(
+        State(Arc::clone(&state)),
+        inbox_principal(),
+        Path(deadline_id),
+        Json(NotificationSnoozeRequest {
+            minutes: Some(60),
+            until: None,
+        }),
+    )
+    .await
+    .expect_err("acknowledged notifications cannot be snoozed");
+    assert_eq!(err.0, StatusCode::NOT_FOUND);
 
-    let no_rate_item = draft
-        .line_items
-        .iter()
-        .find(|item| item.timekeeper.as_deref() == Some("Paralegal"))
-        .expect("no-rate line item");
-    let no_rate_resolution = no_rate_item
-        .rate_resolution
-        .as_ref()
-        .expect("no-rate review should exist");
-    assert_eq!(no_rate_item.rate_source, None);
-    assert_eq!(no_rate_item.resolved_rate, None);
-    assert_eq!(no_rate_item.unit_price, "0");
-    assert_eq!(
-        no_rate_resolution.fallback_reason.as_deref(),
-        Some("no_rate_found")
-    );
-    assert!(no_rate_resolution.fallback_applied);
-    assert!(no_rate_resolution.matched_schedule.is_none());
+    let err = notifications_list_handler(
+        State(Arc::clone(&state)),
+        inbox_principal(),
+        Query(list(Some("later"))),
+    )
+    .await
+    .expect_err("unknown status");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn invoice_ledes_returns_structured_validation_errors_and_ignores_expenses() {
+async fn medical_chronology_cites_record_pages_by_provider() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
+    workspace
+        .write(
+            "matters/demo/medical/records/er_records.md",
+            "Facility: St. Mary's Hospital\n\
+             Date of Service: 03/14/2024    Provider: Dr. Alice Chen\n\
+             Page 1 of 2\n\
+             Diagnoses:\n\
+             - Cervical strain (S13.4XXA)\n\
+             Page 2 of 2\n",
+        )
+        .await
+        .expect("seed er records");
+    workspace
+        .write(
+            "matters/demo/medical/records/physio.md",
+            "Clinic: Lakeside Physiotherapy\nTherapist: J. Patel, PT\n\x0cVisit Date: 2024-04-02\nDx: Neck sprain\n",
+        )
+        .await
+        .expect("seed physio records");
+    workspace
+        .write(
+            "matters/demo/medical/records/billing_ledger.md",
+            "Balance due: $1,240.00\n",
+        )
+        .await
+        .expect("seed ledger");
     let state =
         test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
-        .await
-        .expect("sync matter row");
 
-    let (_status, Json(time_entry)) = matter_time_create_handler(
+    let Json(resp) = medical_chronology_handler(
         State(Arc::clone(&state)),
         owner_principal(),
         Path("demo".to_string()),
-        Json(CreateTimeEntryRequest {
-            timekeeper: "Lead".to_string(),
-            description: "Research issues".to_string(),
-            hours: "1.00".to_string(),
-            hourly_rate: Some("300.00".to_string()),
-            task_code: None,
-            activity_code: None,
-            entry_date: "2026-04-12".to_string(),
-            billable: Some(true),
-            block_billing_flag: None,
-            block_billing_reason: None,
-        }),
     )
     .await
-    .expect("create time entry");
-    let _ = matter_expenses_create_handler(
+    .expect("medical chronology");
+    assert_eq!(resp.records_scanned, 3);
+    assert_eq!(
+        resp.records_without_visits,
+        vec!["matters/demo/medical/records/billing_ledger.md".to_string()]
+    );
+    assert_eq!(resp.visits.len(), 2);
+    assert_eq!(resp.visits[0].provider.as_deref(), Some("Dr. Alice Chen"));
+    assert_eq!(resp.visits[0].citation(), "er_records.md pp. 1-2");
+    assert_eq!(resp.visits[1].citation(), "physio.md p. 2");
+    assert_eq!(resp.providers.len(), 2);
+
+    let (status, Json(export)) = medical_chronology_export_handler(
         State(Arc::clone(&state)),
         owner_principal(),
         Path("demo".to_string()),
-        Json(CreateExpenseEntryRequest {
-            submitted_by: "Lead".to_string(),
-            description: "Filing fee".to_string(),
-            amount: "45.00".to_string(),
-            category: "filing_fee".to_string(),
-            entry_date: "2026-04-12".to_string(),
-            receipt_path: None,
-            billable: Some(true),
-        }),
     )
     .await
-    .expect("create expense entry");
+    .expect("export chronology");
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(export.path, "matters/demo/medical/treatment_chronology.md");
+    assert_eq!(export.visit_count, 2);
+    assert_eq!(export.provider_count, 2);
+    let doc = workspace.read(&export.path).await.expect("read chronology");
+    assert!(doc.content.contains(
+        "| 2024-03-14 | Dr. Alice Chen | St. Mary's Hospital |  | Cervical strain (S13.4XXA) | er_records.md pp. 1-2 |"
+    ));
+    let data = workspace
+        .read(&export.data_path)
+        .await
+        .expect("read visits json");
+    let visits: Vec<crate::legal::medical_records::MedicalVisit> =
+        serde_json::from_str(&data.content).expect("visits json");
+    assert_eq!(visits.len(), 2);
 
-    let (_status, Json(invoice)) = invoices_save_handler(
+    let err = medical_chronology_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(DraftInvoiceRequest {
-            matter_id: "demo".to_string(),
-            invoice_number: "INV-LEDES-ERR".to_string(),
-            due_date: Some("2026-05-10".to_string()),
-            notes: None,
-        }),
+        Path("missing".to_string()),
     )
     .await
-    .expect("save invoice draft");
-    let _ = invoices_finalize_handler(
+    .expect_err("unknown matter");
+    assert_eq!(err.0, StatusCode::NOT_FOUND);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn documents_compare_files_change_memo_for_executed_copy() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    workspace
+        .write(
+            "matters/demo/drafts/msa.md",
+            "# Master Services Agreement\n\n1. Fees. Client shall pay $5,000 per month.\n\n2. Term. One year.\n",
+        )
+        .await
+        .expect("seed draft");
+    workspace
+        .write(
+            "matters/demo/executed/msa.md",
+            "MASTER SERVICES AGREEMENT\n\n1. Fees. Client shall pay $6,000 per\nmonth.\n\nPage 1 of 1\n\n2. Term. One year.\n",
+        )
+        .await
+        .expect("seed executed text");
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+    let request = |write_memo: bool| CompareDocumentsRequest {
+        base_path: "matters/demo/drafts/msa.md".to_string(),
+        compared_path: "matters/demo/executed/msa.md".to_string(),
+        write_memo,
+        title: None,
+    };
+
+    let Json(resp) = matter_documents_compare_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(invoice.invoice.id.clone()),
+        Path("demo".to_string()),
+        Json(request(false)),
     )
     .await
-    .expect("finalize invoice");
+    .expect("compare");
+    assert_eq!(resp.comparison.substantive, 1);
+    assert_eq!(resp.comparison.formatting, 1);
+    assert_eq!(resp.comparison.unchanged, 1);
+    assert!(resp.memo_path.is_none());
 
-    let (status, Json(ledes)) = invoice_ledes_handler(
+    let Json(resp) = matter_documents_compare_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(invoice.invoice.id.clone()),
-        Query(InvoiceLedesQuery {
-            format: Some("98b".to_string()),
-        }),
+        Path("demo".to_string()),
+        Json(request(true)),
     )
     .await
-    .expect("LEDES validation should return structured response");
-    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
-    assert_eq!(ledes.format, "98b");
-    assert!(ledes.content.is_none());
-    assert_eq!(ledes.errors.len(), 1);
-    assert_eq!(
-        ledes.errors[0].time_entry_id.as_deref(),
-        Some(time_entry.id.as_str())
-    );
-    assert_eq!(
-        ledes.errors[0].missing_fields,
-        vec!["task_code".to_string(), "activity_code".to_string()]
+    .expect("compare with memo");
+    let memo_path = resp.memo_path.expect("memo path");
+    assert!(memo_path.starts_with("matters/demo/memos/"), "{memo_path}");
+    assert!(
+        memo_path.ends_with("-changes-from-msa-md-to-msa-md.md"),
+        "{memo_path}"
     );
+    let memo = workspace.read(&memo_path).await.expect("read memo");
+    assert!(memo.content.contains("- Substantive changes: 1\n"));
+    assert!(memo.content.contains("[-$5,000-] {+$6,000+}"));
+    let documents = db
+        .list_matter_documents_db("test-user", "demo")
+        .await
+        .expect("list documents");
+    assert!(documents.iter().any(|doc| doc.path == memo_path));
+
+    let mut outside = request(false);
+    outside.compared_path = "matters/other/executed/msa.md".to_string();
+    let err = matter_documents_compare_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(outside),
+    )
+    .await
+    .expect_err("document outside the matter");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn finalized_invoice_preserves_rate_snapshot_after_schedule_patch() {
+async fn precedents_promote_closed_work_product_and_respect_ethical_walls() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
+    seed_valid_matter(workspace.as_ref(), "newdeal").await;
     let state =
         test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
+    for matter_id in ["demo", "newdeal"] {
+        ensure_matter_db_row_from_workspace(state.as_ref(), matter_id)
+            .await
+            .expect("sync matter row");
+    }
+    workspace
+        .write(
+            "matters/demo/drafts/spa.md",
+            "---\nstatus: final\n---\n# Share Purchase Agreement\n\n\
+             Demo Client sells the Shares for $4,250,000.\n\n\
+             8. Indemnification. The Seller's indemnification obligations are subject to a cap \
+             of 10% of the purchase price.\n\n\
+             9. Notices. Notices to the Seller go to Jordan Vale.\n",
+        )
         .await
-        .expect("sync matter row");
+        .expect("seed source document");
+    // An LLM-extracted name the heuristic pass would miss.
+    db.replace_document_entities(
+        "test-user",
+        "demo",
+        "matters/demo/drafts/spa.md",
+        &[crate::db::CreateDocumentEntityParams {
+            kind: crate::db::DocumentEntityKind::Person,
+            value: "Jordan Vale".to_string(),
+            normalized_value: "jordan vale".to_string(),
+            snippet: None,
+            confidence: 0.9,
+            extractor: "llm".to_string(),
+        }],
+    )
+    .await
+    .expect("seed entity index");
+    let request = |replacements: Vec<crate::legal::precedent::Replacement>,
+                   review_token: Option<String>| PromotePrecedentRequest {
+        path: "matters/demo/drafts/spa.md".to_string(),
+        title: Some("Share purchase agreement, seller-friendly".to_string()),
+        document_type: Some("Share purchase agreement".to_string()),
+        replacements,
+        review_token,
+    };
 
-    let (_status, Json(default_rate)) = billing_rates_create_handler(
+    let err = matter_precedent_promote_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(CreateBillingRateScheduleRequest {
-            matter_id: None,
-            timekeeper: "Lead".to_string(),
-            rate: "425.00".to_string(),
-            effective_start: "2026-01-01".to_string(),
-            effective_end: None,
-        }),
+        Path("demo".to_string()),
+        Json(request(Vec::new(), None)),
     )
     .await
-    .expect("create default rate");
+    .expect_err("open matter");
+    assert_eq!(err.0, StatusCode::CONFLICT);
 
-    let _ = matter_time_create_handler(
+    db.update_matter(
+        "test-user",
+        "demo",
+        &crate::db::UpdateMatterParams {
+            client_id: None,
+            status: Some(crate::db::MatterStatus::Closed),
+            stage: None,
+            practice_area: Some(Some("corporate".to_string())),
+            jurisdiction: None,
+            opened_at: None,
+            closed_at: Some(Some(Utc::now())),
+            assigned_to: None,
+            custom_fields: None,
+        },
+    )
+    .await
+    .expect("close matter");
+    let err = matter_precedent_promote_handler(
         State(Arc::clone(&state)),
         owner_principal(),
         Path("demo".to_string()),
-        Json(CreateTimeEntryRequest {
-            timekeeper: "Lead".to_string(),
-            description: "Prepare witness outline".to_string(),
-            hours: "1.00".to_string(),
-            hourly_rate: None,
-            task_code: Some("b110".to_string()),
-            activity_code: Some("a101".to_string()),
-            entry_date: "2026-04-18".to_string(),
-            billable: Some(true),
-            block_billing_flag: None,
-            block_billing_reason: None,
-        }),
+        Json(request(Vec::new(), None)),
     )
     .await
-    .expect("create time entry");
+    .expect_err("mapping not reviewed");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
 
-    let (_status, Json(invoice)) = invoices_save_handler(
+    let preview = |replacements| AnonymizePrecedentRequest {
+        path: "matters/demo/drafts/spa.md".to_string(),
+        replacements,
+    };
+    let Json(proposal) = matter_precedent_anonymize_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Json(DraftInvoiceRequest {
-            matter_id: "demo".to_string(),
-            invoice_number: "INV-SNAPSHOT-1".to_string(),
-            due_date: Some("2026-05-18".to_string()),
-            notes: None,
-        }),
+        Path("demo".to_string()),
+        Json(preview(None)),
     )
     .await
-    .expect("save invoice draft");
-    let _ = invoices_finalize_handler(
+    .expect("propose mapping");
+    let mapping: Vec<(&str, &str, &str)> = proposal
+        .proposed
+        .iter()
+        .map(|p| (p.original.as_str(), p.placeholder.as_str(), p.source))
+        .collect();
+    assert_eq!(
+        mapping,
+        vec![
+            ("Demo Client", "[Client]", "client"),
+            ("Jordan Vale", "[Person 1]", "entity_index"),
+            ("$4,250,000", "[Amount 1]", "heuristic"),
+        ]
+    );
+    assert!(
+        proposal
+            .preview
+            .contains("[Client] sells the Shares for [Amount 1].")
+    );
+
+    // The attorney renames the amount placeholder and previews the edit.
+    let mut edited = proposal.replacements.clone();
+    for replacement in &mut edited {
+        if replacement.original == "$4,250,000" {
+            replacement.placeholder = "[Purchase Price]".to_string();
+        }
+    }
+    let Json(reviewed) = matter_precedent_anonymize_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(invoice.invoice.id.clone()),
+        Path("demo".to_string()),
+        Json(preview(Some(edited.clone()))),
     )
     .await
-    .expect("finalize invoice");
+    .expect("preview edited mapping");
+    assert_eq!(reviewed.substitutions, 3);
+    assert_ne!(reviewed.review_token, proposal.review_token);
+    let err = matter_precedent_promote_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(request(edited.clone(), Some(proposal.review_token.clone()))),
+    )
+    .await
+    .expect_err("token for another mapping");
+    assert_eq!(err.0, StatusCode::CONFLICT);
+    // A client cannot mint a token for a mapping nobody previewed.
+    let source = workspace
+        .read("matters/demo/drafts/spa.md")
+        .await
+        .expect("read source");
+    let forged = crate::legal::anonymize::review_token(b"", "test-user", &source.content, &edited);
+    let err = matter_precedent_promote_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("demo".to_string()),
+        Json(request(edited.clone(), Some(forged))),
+    )
+    .await
+    .expect_err("token without the server key");
+    assert_eq!(err.0, StatusCode::CONFLICT);
 
-    let Json(updated_rate) = billing_rates_patch_handler(
+    let (status, Json(promoted)) = matter_precedent_promote_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(default_rate.id.clone()),
-        Json(UpdateBillingRateScheduleRequest {
-            matter_id: None,
-            timekeeper: None,
-            rate: Some("500.00".to_string()),
-            effective_start: None,
-            effective_end: None,
+        Path("demo".to_string()),
+        Json(request(edited, Some(reviewed.review_token))),
+    )
+    .await
+    .expect("promote");
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(promoted.substitutions, 3);
+    assert_eq!(promoted.precedent.source_matter_id, "demo");
+    assert_eq!(
+        promoted.precedent.practice_area.as_deref(),
+        Some("corporate")
+    );
+    assert!(
+        promoted
+            .precedent
+            .path
+            .starts_with("precedent/share-purchase-agreement-seller-friendly-")
+    );
+    let copy = workspace
+        .read(&promoted.precedent.path)
+        .await
+        .expect("read precedent");
+    assert!(
+        copy.content
+            .contains("[Client] sells the Shares for [Purchase Price].")
+    );
+    assert!(
+        copy.content
+            .contains("Notices to the Seller go to [Person 1].")
+    );
+    assert!(!copy.content.contains("Demo Client"));
+    assert!(!copy.content.contains("demo"));
+    assert!(!copy.content.contains("status: final"));
+
+    let query = || PrecedentSuggestQuery {
+        q: "indemnification cap".to_string(),
+        limit: None,
+    };
+    let Json(suggested) = matter_precedent_suggest_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path("newdeal".to_string()),
+        Query(query()),
+    )
+    .await
+    .expect("suggest");
+    assert_eq!(suggested.suggestions.len(), 1);
+    let suggestion = &suggested.suggestions[0];
+    assert_eq!(suggestion.source_matter_id, "demo");
+    assert_eq!(suggestion.similarity, 1.0);
+    assert!(suggestion.excerpt.contains("Indemnification"));
+
+    // Alice works on the new deal but is walled off from the source matter.
+    db.ensure_user_account("alice", "Alice", UserRole::Attorney)
+        .await
+        .expect("create member");
+    db.upsert_matter_membership(&crate::db::UpsertMatterMembershipParams {
+        matter_owner_user_id: "test-user".to_string(),
+        matter_id: "newdeal".to_string(),
+        member_user_id: "alice".to_string(),
+        role: crate::db::MatterMemberRole::Collaborator,
+    })
+    .await
+    .expect("add member");
+    let alice = || principal_with_role("alice", UserRole::Attorney);
+    let Json(suggested) = matter_precedent_suggest_handler(
+        State(Arc::clone(&state)),
+        alice(),
+        Path("newdeal".to_string()),
+        Query(query()),
+    )
+    .await
+    .expect("suggest behind wall");
+    assert!(suggested.suggestions.is_empty());
+    let Json(listed) = precedents_list_handler(State(Arc::clone(&state)), alice())
+        .await
+        .expect("list as alice");
+    assert!(listed.precedents.is_empty());
+    let Json(listed) = precedents_list_handler(State(Arc::clone(&state)), owner_principal())
+        .await
+        .expect("list as owner");
+    assert_eq!(listed.precedents.len(), 1);
+}
+
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn immigration_forms_fill_from_case_data_and_export_xfdf() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    seed_valid_matter(workspace.as_ref(), "demo").await;
+    let case_data = "applicant:\n  family_name: Okafor\n  given_name: Chidi\n  date_of_birth: 1987-02-14\n  country_of_birth: Nigeria\n  country_of_citizenship: Nigeria\n  a_number: A-1234567\n";
+    workspace
+        .write("matters/demo/immigration/case_data.yaml", case_data)
+        .await
+        .expect("seed case data");
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+
+    let Json(catalog) = legal_immigration_forms_handler()
+        .await
+        .expect("form catalog");
+    let i765 = catalog
+        .forms
+        .iter()
+        .find(|form| form.id == "i-765")
+        .expect("i-765 bundled");
+    assert!(
+        i765.deadline_presets
+            .iter()
+            .any(|rule| rule.id == "uscis_biometrics")
+    );
+
+    let Json(resp) = matter_immigration_form_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(("demo".to_string(), "I765".to_string())),
+    )
+    .await
+    .expect("fill i-765");
+    assert_eq!(
+        resp.case_data_path,
+        "matters/demo/immigration/case_data.yaml"
+    );
+    assert_eq!(resp.form.form_id, "i-765");
+    assert!(!resp.form.complete);
+    assert_eq!(
+        resp.form.missing,
+        vec![
+            "Part 2: Mailing address".to_string(),
+            "Part 2: Eligibility category, e.g. (c)(9)".to_string(),
+        ]
+    );
+    assert!(
+        resp.deadline_presets
+            .iter()
+            .any(|rule| rule.id == "uscis_rfe_response")
+    );
+
+    let err = matter_immigration_form_export_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(("demo".to_string(), "i-765".to_string())),
+        Json(ImmigrationFormExportRequest {
+            format: Some("xfdf".to_string()),
+            allow_incomplete: false,
+        }),
+    )
+    .await
+    .expect_err("incomplete forms are held back");
+    assert_eq!(err.0, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(err.1.contains("Eligibility category"));
+
+    workspace
+        .write(
+            "matters/demo/immigration/case_data.yaml",
+            &format!(
+                "{case_data}  mailing_address: 12 Elm St, Springfield IL 62701\nead:\n  eligibility_category: (c)(9)\n"
+            ),
+        )
+        .await
+        .expect("complete case data");
+    let (status, Json(export)) = matter_immigration_form_export_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(("demo".to_string(), "i-765".to_string())),
+        Json(ImmigrationFormExportRequest {
+            format: Some("xfdf".to_string()),
+            allow_incomplete: false,
         }),
     )
     .await
-    .expect("patch billing rate");
-    assert_eq!(updated_rate.rate, "500.00");
+    .expect("export xfdf");
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(export.complete);
+    assert_eq!(export.path, "matters/demo/immigration/forms/i-765.xfdf");
+    let doc = workspace.read(&export.path).await.expect("read xfdf");
+    assert!(
+        doc.content
+            .contains("<field name=\"applicant_a_number\"><value>A001234567</value></field>")
+    );
+    assert!(
+        doc.content
+            .contains("<field name=\"applicant_date_of_birth\"><value>02/14/1987</value></field>")
+    );
 
-    let Json(detail) = invoices_get_handler(
+    let err = matter_immigration_form_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path(invoice.invoice.id.clone()),
+        Path(("demo".to_string(), "i-9999".to_string())),
     )
     .await
-    .expect("load finalized invoice detail");
-    let line_item = detail.line_items.first().expect("finalized line item");
-    assert_eq!(line_item.rate_source.as_deref(), Some("timekeeper_default"));
-    assert_eq!(
-        line_item
-            .resolved_rate
-            .as_deref()
-            .expect("resolved rate")
-            .parse::<rust_decimal::Decimal>()
-            .expect("parse resolved rate"),
-        rust_decimal::Decimal::new(42500, 2)
-    );
-    assert_eq!(
-        line_item
-            .unit_price
-            .parse::<rust_decimal::Decimal>()
-            .expect("parse unit price"),
-        rust_decimal::Decimal::new(42500, 2)
-    );
+    .expect_err("unknown form");
+    assert_eq!(err.0, StatusCode::NOT_FOUND);
 }
 
 #[cfg(feature = "libsql")]
 #[tokio::test]
-async fn matter_detail_work_and_finance_endpoints_return_expected_data() {
+async fn ip_docket_chains_trademark_terms_to_registration_anchor() {
     let (db, _tmp) = crate::testing::test_db().await;
     let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
     seed_valid_matter(workspace.as_ref(), "demo").await;
     let state =
         test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
-    ensure_matter_db_row_from_workspace(state.as_ref(), "demo")
-        .await
-        .expect("sync matter row");
+    let store = state.store.as_ref().expect("store should exist");
 
-    let _ = matter_tasks_create_handler(
+    let Json(catalog) = legal_ip_rules_handler().await.expect("ip rule catalog");
+    let ukipo = catalog
+        .offices
+        .iter()
+        .find(|office| office.code == "UKIPO")
+        .expect("UKIPO listed");
+    assert!(
+        ukipo
+            .rules
+            .iter()
+            .any(|info| info.rule.id == "ukipo_tm_renewal" && info.rule.offset_months == 120)
+    );
+
+    let request = |registration_date: &str, save: bool| IpDocketRequest {
+        office: "US".to_string(),
+        right: "trademark".to_string(),
+        application_number: Some("Reg. No. 6,012,345".to_string()),
+        filing_date: None,
+        priority_date: None,
+        grant_date: None,
+        registration_date: Some(registration_date.to_string()),
+        office_action_date: None,
+        allowance_date: None,
+        include_treaty: None,
+        reminder_days: vec![30],
+        save,
+    };
+
+    let Json(preview) = matter_ip_docket_handler(
         State(Arc::clone(&state)),
         owner_principal(),
         Path("demo".to_string()),
-        Json(CreateMatterTaskRequest {
-            title: "Draft chronology".to_string(),
-            description: Some("Capture filing timeline".to_string()),
-            status: Some("todo".to_string()),
-            assignee: Some("Paralegal".to_string()),
-            due_at: None,
-            blocked_by: Vec::new(),
-        }),
+        Json(request("2020-05-14", false)),
     )
     .await
-    .expect("create task");
+    .expect("preview");
+    assert_eq!(preview.office, "USPTO");
+    assert_eq!(preview.application, "USPTO trademark Reg. No. 6,012,345");
+    let ids = preview
+        .entries
+        .iter()
+        .map(|entry| entry.rule.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ids,
+        vec![
+            "uspto_tm_section_8",
+            "uspto_tm_section_8_grace",
+            "uspto_tm_renewal",
+            "uspto_tm_renewal_grace"
+        ]
+    );
+    // 2026-11-14 is a Saturday; the grace period rolls to Monday.
+    assert!(preview.entries[1].deadline.due_at.starts_with("2026-11-16"));
+    assert!(preview.anchors.is_empty() && preview.saved.is_empty());
 
-    let _ = matter_notes_create_handler(
+    let Json(saved) = matter_ip_docket_handler(
         State(Arc::clone(&state)),
         owner_principal(),
         Path("demo".to_string()),
-        Json(CreateMatterNoteRequest {
-            author: "Lead".to_string(),
-            body: "Initial intake complete".to_string(),
-            pinned: true,
-        }),
+        Json(request("2020-05-14", true)),
     )
     .await
-    .expect("create note");
+    .expect("save docket");
+    assert_eq!(saved.anchors.len(), 1);
+    let anchor = &saved.anchors[0];
+    assert!(anchor.completed_at.is_some());
+    assert_eq!(saved.saved.len(), 4);
+    assert!(
+        saved
+            .saved
+            .iter()
+            .all(|record| record.computed_from.as_deref() == Some(anchor.id.as_str()))
+    );
 
-    let _ = matter_time_create_handler(
+    let Json(again) = matter_ip_docket_handler(
         State(Arc::clone(&state)),
         owner_principal(),
         Path("demo".to_string()),
-        Json(CreateTimeEntryRequest {
-            timekeeper: "Lead".to_string(),
-            description: "Case strategy review".to_string(),
-            hours: "1.25".to_string(),
-            hourly_rate: Some("300".to_string()),
-            task_code: None,
-            activity_code: None,
-            entry_date: "2026-04-12".to_string(),
-            billable: Some(true),
-            block_billing_flag: None,
-            block_billing_reason: None,
-        }),
+        Json(request("2020-05-14", true)),
     )
     .await
-    .expect("create time entry");
+    .expect("re-run is idempotent");
+    assert!(again.anchors.is_empty() && again.saved.is_empty());
+    assert!(
+        again
+            .entries
+            .iter()
+            .all(|entry| entry.existing_deadline_id.is_some())
+    );
 
-    let _ = matter_expenses_create_handler(
+    let err = matter_ip_docket_handler(
         State(Arc::clone(&state)),
         owner_principal(),
         Path("demo".to_string()),
-        Json(CreateExpenseEntryRequest {
-            submitted_by: "Lead".to_string(),
-            description: "Filing courier".to_string(),
-            amount: "45.00".to_string(),
-            category: "other".to_string(),
-            entry_date: "2026-04-12".to_string(),
-            receipt_path: None,
-            billable: Some(true),
+        Json(request("2020-06-15", true)),
+    )
+    .await
+    .expect_err("moved event conflicts with anchor");
+    assert_eq!(err.0, StatusCode::CONFLICT);
+    assert!(err.1.contains(&anchor.id));
+
+    let Json(_moved) = matter_deadlines_patch_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Path(("demo".to_string(), anchor.id.clone())),
+        Json(UpdateMatterDeadlineRequest {
+            title: None,
+            deadline_type: None,
+            due_at: Some("2020-06-15".to_string()),
+            completed_at: None,
+            reminder_days: None,
+            rule_ref: None,
+            computed_from: None,
+            task_id: None,
+            is_unsupported: None,
         }),
     )
     .await
-    .expect("create expense entry");
+    .expect("patch anchor");
+    let section_8 = saved
+        .saved
+        .iter()
+        .find(|record| record.title.ends_with("Section 8 declaration of use"))
+        .expect("section 8 saved");
+    let after = store
+        .get_matter_deadline(
+            &state.user_id,
+            "demo",
+            Uuid::parse_str(&section_8.id).expect("uuid"),
+        )
+        .await
+        .expect("load section 8")
+        .expect("section 8 exists");
+    assert_eq!(after.due_at.date_naive().to_string(), "2026-06-15");
 
-    let _ = matter_trust_deposit_handler(
+    let err = matter_ip_docket_handler(
         State(Arc::clone(&state)),
         owner_principal(),
         Path("demo".to_string()),
-        Json(TrustDepositRequest {
-            amount: "500.00".to_string(),
-            recorded_by: "Lead".to_string(),
-            description: Some("Initial retainer".to_string()),
-            reference_number: None,
+        Json(IpDocketRequest {
+            office: "JPO".to_string(),
+            ..request("2020-05-14", false)
         }),
     )
     .await
-    .expect("create trust deposit");
+    .expect_err("unknown office");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+}
 
-    let Json(tasks) = matter_tasks_list_handler(
+#[cfg(feature = "libsql")]
+#[tokio::test]
+async fn custom_field_schemas_validate_matters_and_feed_search() {
+    let (db, _tmp) = crate::testing::test_db().await;
+    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+    let state =
+        test_gateway_state_with_store_and_workspace(Arc::clone(&db), Arc::clone(&workspace));
+
+    let err = custom_field_schemas_put_handler(
         State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".into()),
+        Json(CustomFieldSchemasPayload {
+            schemas: serde_json::json!([
+                {"id": "pi", "label": "PI", "fields": [{"key": "liability", "label": "Liability", "type": "choice"}]}
+            ]),
+        }),
     )
     .await
-    .expect("list tasks");
-    assert_eq!(tasks.tasks.len(), 1);
+    .expect_err("choice without options");
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
 
-    let Json(notes) = matter_notes_list_handler(
+    let _ = custom_field_schemas_put_handler(
         State(Arc::clone(&state)),
-        owner_principal(),
-        Path("demo".into()),
+        Json(CustomFieldSchemasPayload {
+            schemas: serde_json::json!([
+                {
+                    "id": "personal_injury",
+                    "label": "Personal injury",
+                    "practice_areas": ["personal injury"],
+                    "fields": [
+                        {"key": "injury_date", "label": "Date of injury", "type": "date", "required": true},
+                        {"key": "policy_limit", "label": "Policy limit", "type": "number"},
+                        {"key": "adjuster", "label": "Adjuster", "type": "text", "searchable": true}
+                    ]
+                }
+            ]),
+        }),
     )
     .await
-    .expect("list notes");
-    assert_eq!(notes.notes.len(), 1);
+    .expect("save schemas");
+    let Json(stored) = custom_field_schemas_get_handler(State(Arc::clone(&state)))
+        .await
+        .expect("read schemas");
+    assert_eq!(stored.schemas[0]["fields"][2]["key"], "adjuster");
 
-    let Json(time_entries) = matter_time_list_handler(
+    let create = |matter_id: &str, client: &str, custom_fields: serde_json::Value| {
+        let state = Arc::clone(&state);
+        let req = CreateMatterRequest {
+            matter_id: matter_id.to_string(),
+            client: client.to_string(),
+            confidentiality: "attorney-client-privileged".to_string(),
+            retention: "follow-firm-policy".to_string(),
+            jurisdiction: None,
+            practice_area: Some("Personal Injury".to_string()),
+            opened_date: None,
+            opened_at: None,
+            team: vec![],
+            adversaries: vec![],
+            conflict_decision: None,
+            conflict_note: None,
+            custom_fields: Some(custom_fields),
+        };
+        async move { matters_create_handler(State(state), owner_principal(), Json(req)).await }
+    };
+
+    let err = create(
+        "ruiz-1",
+        "Dana Ruiz",
+        serde_json::json!({"policy_limit": "100k"}),
+    )
+    .await
+    .expect_err("missing and invalid fields");
+    assert_eq!(err.0, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(err.1.contains("'Date of injury' is required"));
+    assert!(err.1.contains("'Policy limit' must be a number"));
+
+    let (status, _) = create(
+        "ruiz-2",
+        "Dana Ruiz",
+        serde_json::json!({
+            "injury_date": "08/02/2025",
+            "policy_limit": "100,000",
+            "adjuster": "Morgan Whitlock",
+            "court": "Kings County"
+        }),
+    )
+    .await
+    .expect("create with custom fields");
+    assert_eq!(status, StatusCode::CREATED);
+    let store = state.store.as_ref().expect("store should exist");
+    let row = store
+        .get_matter_db(&state.user_id, "ruiz-2")
+        .await
+        .expect("load matter")
+        .expect("matter row");
+    assert_eq!(
+        row.custom_fields,
+        serde_json::json!({
+            "injury_date": "2025-08-02",
+            "policy_limit": 100000,
+            "adjuster": "Morgan Whitlock",
+            "court": "Kings County"
+        })
+    );
+
+    let err = matter_patch_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path("demo".into()),
+        Path("ruiz-2".to_string()),
+        Json(
+            serde_json::from_value(serde_json::json!({
+                "custom_fields": {"injury_date": "last spring"}
+            }))
+            .unwrap(),
+        ),
     )
     .await
-    .expect("list time entries");
-    assert_eq!(time_entries.entries.len(), 1);
+    .expect_err("invalid date on update");
+    assert_eq!(err.0, StatusCode::UNPROCESSABLE_ENTITY);
 
-    let Json(expense_entries) = matter_expenses_list_handler(
+    // Moving the matter out of personal injury drops the requirement.
+    let _ = matter_patch_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path("demo".into()),
+        Path("ruiz-2".to_string()),
+        Json(
+            serde_json::from_value(serde_json::json!({
+                "practice_area": "Employment",
+                "custom_fields": {"adjuster": "Morgan Whitlock"}
+            }))
+            .unwrap(),
+        ),
     )
     .await
-    .expect("list expense entries");
-    assert_eq!(expense_entries.entries.len(), 1);
+    .expect("schema no longer applies");
 
-    let Json(summary) = matter_time_summary_handler(
+    matter_patch_handler(
         State(Arc::clone(&state)),
         owner_principal(),
-        Path("demo".into()),
+        Path("ruiz-2".to_string()),
+        Json(
+            serde_json::from_value(serde_json::json!({ "practice_area": "personal injury" }))
+                .unwrap(),
+        ),
     )
     .await
-    .expect("time summary");
-    let total_hours = summary
-        .total_hours
-        .parse::<rust_decimal::Decimal>()
-        .expect("hours decimal");
-    assert_eq!(total_hours, rust_decimal::Decimal::new(125, 2));
+    .expect_err("current values fail the restored schema");
 
-    let Json(ledger) =
-        matter_trust_ledger_handler(State(state), owner_principal(), Path("demo".into()))
-            .await
-            .expect("trust ledger");
-    assert_eq!(ledger.matter_id, "demo");
-    assert_eq!(ledger.entries.len(), 1);
-}
-
-#[cfg(feature = "libsql")]
-#[tokio::test]
-async fn memory_write_handler_invalidates_conflict_cache() {
-    crate::legal::matter::reset_conflict_cache_for_tests();
-    let (db, _tmp) = crate::testing::test_db().await;
-    let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
-    let mut legal = test_legal_config();
-    legal.require_matter_context = false;
-    let state = test_gateway_state_with_store_workspace_and_legal(
-        Arc::clone(&db),
-        Arc::clone(&workspace),
-        legal,
-    );
-
-    workspace
-        .write(
-            "conflicts.json",
-            r#"[{"name":"Alpha Holdings","aliases":["Alpha"]}]"#,
-        )
-        .await
-        .expect("seed conflicts");
-
-    let mut legal = crate::config::LegalConfig::resolve(&crate::settings::Settings::default())
-        .expect("default legal config should resolve");
-    legal.active_matter = None;
-    legal.enabled = true;
-    legal.conflict_check_enabled = true;
-
-    let first =
-        crate::legal::matter::detect_conflict(workspace.as_ref(), &legal, "Alpha Holdings").await;
-    assert_eq!(first.as_deref(), Some("Alpha Holdings"));
-    assert_eq!(
-        crate::legal::matter::conflict_cache_refresh_count_for_tests(),
-        1
-    );
-
-    let write_result = memory_write_handler(
-        State(state),
-        Json(MemoryWriteRequest {
-            path: "conflicts.json".to_string(),
-            content: r#"[{"name":"Beta Partners","aliases":["Beta"]}]"#.to_string(),
+    let (_, _) = create(
+        "okafor-1",
+        "Chidi Okafor",
+        serde_json::json!({"injury_date": "2025-01-10", "adjuster": "Priya Castellano"}),
+    )
+    .await
+    .expect("create second matter");
+    let Json(results) = global_search_handler(
+        State(Arc::clone(&state)),
+        owner_principal(),
+        Query(GlobalSearchQuery {
+            q: "Castellano".to_string(),
+            types: Some("matter".to_string()),
+            limit: None,
         }),
     )
     .await
-    .expect("memory write should succeed");
-    assert_eq!(write_result.path, "conflicts.json");
-
-    let second =
-        crate::legal::matter::detect_conflict(workspace.as_ref(), &legal, "Beta Partners").await;
-    assert_eq!(second.as_deref(), Some("Beta Partners"));
-    assert_eq!(
-        crate::legal::matter::conflict_cache_refresh_count_for_tests(),
-        2
+    .expect("search");
+    assert_eq!(results.results.len(), 1);
+    assert_eq!(results.results[0].id, "okafor-1");
+    assert!(
+        results.results[0]
+            .snippet
+            .as_deref()
+            .is_some_and(|snippet| snippet.contains("Adjuster: Priya Castellano"))
     );
 }
@@ -1,15 +1,25 @@
 //! SSE connection manager for broadcasting events to browser tabs.
-
+//!
+//! Every broadcast event gets an id and is kept in a short replay buffer.
+//! A browser that reconnects with `Last-Event-ID` (which `EventSource` sends
+//! on its own) receives the events it missed, and a connection that falls
+//! behind its bounded queue catches up from the same buffer instead of
+//! silently skipping events. When the missed events are no longer buffered
+//! the client gets a `resync` event and should reload its state.
+//!
+//! Ids start at the process start time in microseconds, so an id from before
+//! a restart is always older than the buffer and triggers a resync.
+
+use std::collections::VecDeque;
 use std::convert::Infallible;
-use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::sse::{Event, KeepAlive};
 use futures::Stream;
 use tokio::sync::broadcast;
 use tokio_stream::StreamExt;
-use tokio_stream::wrappers::BroadcastStream;
 
 use crate::channels::web::types::SseEvent;
 
@@ -17,9 +27,175 @@ use crate::channels::web::types::SseEvent;
 /// Prevents resource exhaustion from connection flooding.
 const MAX_CONNECTIONS: u64 = 100;
 
+/// Events queued per connection before it counts as lagging and catches up
+/// from the replay buffer.
+const CONNECTION_BUFFER: usize = 256;
+
+/// Recent events kept for `Last-Event-ID` resume and lag recovery.
+const REPLAY_CAPACITY: usize = 1024;
+
+/// Groups of events a client can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SseTopic {
+    /// Conversation activity: responses, tool calls, approvals, auth prompts.
+    Chat,
+    /// Sandbox job progress.
+    Jobs,
+    /// Matter notes and domain events.
+    Matters,
+    /// Server log lines (from the log broadcaster; not replayed).
+    Logs,
+}
+
+impl SseTopic {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "chat" => Some(Self::Chat),
+            "jobs" => Some(Self::Jobs),
+            "matters" => Some(Self::Matters),
+            "logs" => Some(Self::Logs),
+            _ => None,
+        }
+    }
+
+    /// Topic of `event`, or `None` for events every subscriber receives
+    /// (notifications and heartbeats).
+    pub fn of(event: &SseEvent) -> Option<Self> {
+        match event {
+            SseEvent::Response { .. }
+            | SseEvent::Thinking { .. }
+            | SseEvent::ToolStarted { .. }
+            | SseEvent::ToolCompleted { .. }
+            | SseEvent::ToolResult { .. }
+            | SseEvent::StreamChunk { .. }
+            | SseEvent::Status { .. }
+            | SseEvent::ApprovalNeeded { .. }
+            | SseEvent::AuthRequired { .. }
+            | SseEvent::AuthCompleted { .. }
+            | SseEvent::Error { .. }
+            | SseEvent::Typing { .. } => Some(Self::Chat),
+            SseEvent::JobStarted { .. }
+            | SseEvent::JobMessage { .. }
+            | SseEvent::JobToolUse { .. }
+            | SseEvent::JobToolResult { .. }
+            | SseEvent::JobStatus { .. }
+            | SseEvent::JobResult { .. } => Some(Self::Jobs),
+            SseEvent::MatterNote { .. }
+            | SseEvent::MatterNoteMention { .. }
+            | SseEvent::Domain { .. } => Some(Self::Matters),
+            SseEvent::Notification(_) | SseEvent::Heartbeat => None,
+        }
+    }
+}
+
+/// The topics one connection receives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SseTopics {
+    pub chat: bool,
+    pub jobs: bool,
+    pub matters: bool,
+    pub logs: bool,
+}
+
+impl Default for SseTopics {
+    /// Everything except logs, which have their own stream at
+    /// `/api/logs/events` and would flood a chat tab.
+    fn default() -> Self {
+        Self {
+            chat: true,
+            jobs: true,
+            matters: true,
+            logs: false,
+        }
+    }
+}
+
+impl SseTopics {
+    /// Parse a comma-separated topic list (`chat,jobs`).
+    pub fn parse(list: &str) -> Result<Self, String> {
+        let mut topics = Self {
+            chat: false,
+            jobs: false,
+            matters: false,
+            logs: false,
+        };
+        for name in list.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            match SseTopic::parse(name) {
+                Some(SseTopic::Chat) => topics.chat = true,
+                Some(SseTopic::Jobs) => topics.jobs = true,
+                Some(SseTopic::Matters) => topics.matters = true,
+                Some(SseTopic::Logs) => topics.logs = true,
+                None => {
+                    return Err(format!(
+                        "Unknown topic '{name}' (expected chat, jobs, matters, or logs)"
+                    ));
+                }
+            }
+        }
+        Ok(topics)
+    }
+
+    pub fn allows(&self, event: &SseEvent) -> bool {
+        match SseTopic::of(event) {
+            Some(SseTopic::Chat) => self.chat,
+            Some(SseTopic::Jobs) => self.jobs,
+            Some(SseTopic::Matters) => self.matters,
+            Some(SseTopic::Logs) => self.logs,
+            None => true,
+        }
+    }
+}
+
+/// A broadcast event with its replay id.
+#[derive(Debug, Clone)]
+pub struct SequencedEvent {
+    pub id: u64,
+    pub event: SseEvent,
+}
+
+/// What a connection's stream yields.
+#[derive(Debug, Clone)]
+pub enum Delivery {
+    Event(Box<SequencedEvent>),
+    /// Events after `after` were lost; the client should reload its state.
+    Resync {
+        after: u64,
+    },
+}
+
+struct ReplayBuffer {
+    next_id: u64,
+    events: VecDeque<SequencedEvent>,
+}
+
+impl ReplayBuffer {
+    /// Events after `after`, preceded by a resync marker when some of them
+    /// are no longer buffered.
+    fn since(&self, after: u64) -> Vec<Delivery> {
+        let newest = self.next_id.saturating_sub(1);
+        if after >= newest {
+            return Vec::new();
+        }
+        let oldest = self.events.front().map_or(self.next_id, |e| e.id);
+        let mut missed = Vec::new();
+        if after + 1 < oldest {
+            missed.push(Delivery::Resync { after });
+        }
+        missed.extend(
+            self.events
+                .iter()
+                .filter(|e| e.id > after)
+                .cloned()
+                .map(|event| Delivery::Event(Box::new(event))),
+        );
+        missed
+    }
+}
+
 /// Manages SSE broadcast to all connected browser tabs.
 pub struct SseManager {
-    tx: broadcast::Sender<SseEvent>,
+    tx: broadcast::Sender<SequencedEvent>,
+    replay: Arc<Mutex<ReplayBuffer>>,
     connection_count: Arc<AtomicU64>,
     max_connections: u64,
 }
@@ -27,10 +203,16 @@ pub struct SseManager {
 impl SseManager {
     /// Create a new SSE manager.
     pub fn new() -> Self {
-        // Buffer 256 events; slow clients will miss events (acceptable for SSE with reconnect)
-        let (tx, _) = broadcast::channel(256);
+        let (tx, _) = broadcast::channel(CONNECTION_BUFFER);
+        let first_id = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(1, |d| d.as_micros() as u64);
         Self {
             tx,
+            replay: Arc::new(Mutex::new(ReplayBuffer {
+                next_id: first_id,
+                events: VecDeque::with_capacity(REPLAY_CAPACITY),
+            })),
             connection_count: Arc::new(AtomicU64::new(0)),
             max_connections: MAX_CONNECTIONS,
         }
@@ -38,8 +220,23 @@ impl SseManager {
 
     /// Broadcast an event to all connected clients.
     pub fn broadcast(&self, event: SseEvent) {
+        // Numbered and sent under the lock so subscribers see ids in order
+        // and a new subscriber's snapshot lines up with its live feed.
+        let mut replay = self.replay.lock().unwrap_or_else(|e| e.into_inner());
+        let sequenced = SequencedEvent {
+            id: replay.next_id,
+            event,
+        };
+        replay.next_id += 1;
+        // Heartbeats and typing indicators carry no state worth replaying.
+        if !is_ephemeral(&sequenced.event) {
+            if replay.events.len() >= REPLAY_CAPACITY {
+                replay.events.pop_front();
+            }
+            replay.events.push_back(sequenced.clone());
+        }
         // Ignore send errors (no receivers is fine)
-        let _ = self.tx.send(event);
+        let _ = self.tx.send(sequenced);
     }
 
     /// Get current number of active connections.
@@ -47,13 +244,8 @@ impl SseManager {
         self.connection_count.load(Ordering::Relaxed)
     }
 
-    /// Create a raw broadcast subscription for non-SSE consumers (e.g. WebSocket).
-    ///
-    /// Returns a stream of `SseEvent` values and increments/decrements the
-    /// connection counter on creation/drop, just like `subscribe()` does for SSE.
-    ///
-    /// Returns `None` if the maximum connection limit has been reached.
-    pub fn subscribe_raw(&self) -> Option<impl Stream<Item = SseEvent> + Send + 'static + use<>> {
+    /// Reserve a connection slot, or `None` at the connection limit.
+    fn acquire(&self) -> Option<Arc<AtomicU64>> {
         // Atomically increment only if below the limit. This prevents
         // concurrent callers from overshooting max_connections.
         let counter = Arc::clone(&self.connection_count);
@@ -67,73 +259,91 @@ impl SseManager {
                 }
             })
             .ok()?;
-        let rx = self.tx.subscribe();
+        Some(counter)
+    }
 
-        let stream = BroadcastStream::new(rx).filter_map(|result| result.ok());
+    /// Subscribe to deliveries after `last_event_id` (or from now).
+    ///
+    /// Returns `None` if the maximum connection limit has been reached.
+    pub fn subscribe_deliveries(
+        &self,
+        last_event_id: Option<u64>,
+    ) -> Option<impl Stream<Item = Delivery> + Send + 'static + use<>> {
+        let counter = self.acquire()?;
+        let (rx, pending, last_id) = {
+            let replay = self.replay.lock().unwrap_or_else(|e| e.into_inner());
+            let rx = self.tx.subscribe();
+            let newest = replay.next_id.saturating_sub(1);
+            let pending = match last_event_id {
+                Some(after) => replay.since(after),
+                None => Vec::new(),
+            };
+            (rx, VecDeque::from(pending), newest)
+        };
+        let cursor = Cursor {
+            rx,
+            replay: Arc::clone(&self.replay),
+            last_id,
+            pending,
+        };
+        let stream = futures::stream::unfold(cursor, |mut cursor| async move {
+            let delivery = cursor.next().await?;
+            Some((delivery, cursor))
+        });
 
         Some(CountedStream {
-            inner: stream,
+            inner: Box::pin(stream),
             counter,
         })
     }
 
-    /// Create a new SSE stream for a client connection.
+    /// Create a raw broadcast subscription for non-SSE consumers (e.g. WebSocket).
+    ///
+    /// Returns a stream of `SseEvent` values and increments/decrements the
+    /// connection counter on creation/drop, just like `subscribe_events()` does for SSE.
+    /// Lagging consumers catch up from the replay buffer.
+    ///
+    /// Returns `None` if the maximum connection limit has been reached.
+    pub fn subscribe_raw(&self) -> Option<impl Stream<Item = SseEvent> + Send + 'static + use<>> {
+        let stream = self.subscribe_deliveries(None)?;
+        Some(stream.filter_map(|delivery| match delivery {
+            Delivery::Event(sequenced) => Some(sequenced.event),
+            Delivery::Resync { .. } => None,
+        }))
+    }
+
+    /// SSE events for a client connection: the events after `last_event_id`
+    /// first, then live ones, limited to `topics`. Log lines are not part of
+    /// this stream; callers merge them in when `topics.logs` is set.
     ///
     /// Returns `None` if the maximum connection limit has been reached.
-    pub fn subscribe(
+    pub fn subscribe_events(
         &self,
-    ) -> Option<Sse<impl Stream<Item = Result<Event, Infallible>> + Send + 'static + use<>>> {
-        // Atomically increment only if below the limit.
-        let counter = Arc::clone(&self.connection_count);
-        let max = self.max_connections;
-        counter
-            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
-                if current < max {
-                    Some(current + 1)
-                } else {
-                    None
+        last_event_id: Option<u64>,
+        topics: SseTopics,
+    ) -> Option<impl Stream<Item = Result<Event, Infallible>> + Send + 'static + use<>> {
+        let stream = self.subscribe_deliveries(last_event_id)?;
+        Some(stream.filter_map(move |delivery| {
+            match delivery {
+                Delivery::Event(sequenced) if topics.allows(&sequenced.event) => {
+                    let data = serde_json::to_string(&sequenced.event).unwrap_or_default();
+                    let event = Event::default()
+                        .event(event_name(&sequenced.event))
+                        .data(data);
+                    // Ephemeral events are not buffered, so they must not
+                    // move the client's resume point.
+                    Some(Ok(if is_ephemeral(&sequenced.event) {
+                        event
+                    } else {
+                        event.id(sequenced.id.to_string())
+                    }))
                 }
-            })
-            .ok()?;
-        let rx = self.tx.subscribe();
-
-        let stream = BroadcastStream::new(rx)
-            .filter_map(|result| result.ok())
-            .map(|event| {
-                let data = serde_json::to_string(&event).unwrap_or_default();
-                let event_type = match &event {
-                    SseEvent::Response { .. } => "response",
-                    SseEvent::Thinking { .. } => "thinking",
-                    SseEvent::ToolStarted { .. } => "tool_started",
-                    SseEvent::ToolCompleted { .. } => "tool_completed",
-                    SseEvent::ToolResult { .. } => "tool_result",
-                    SseEvent::StreamChunk { .. } => "stream_chunk",
-                    SseEvent::Status { .. } => "status",
-                    SseEvent::ApprovalNeeded { .. } => "approval_needed",
-                    SseEvent::AuthRequired { .. } => "auth_required",
-                    SseEvent::AuthCompleted { .. } => "auth_completed",
-                    SseEvent::Error { .. } => "error",
-                    SseEvent::JobStarted { .. } => "job_started",
-                    SseEvent::JobMessage { .. } => "job_message",
-                    SseEvent::JobToolUse { .. } => "job_tool_use",
-                    SseEvent::JobToolResult { .. } => "job_tool_result",
-                    SseEvent::JobStatus { .. } => "job_status",
-                    SseEvent::JobResult { .. } => "job_result",
-                    SseEvent::Heartbeat => "heartbeat",
-                };
-                Ok(Event::default().event(event_type).data(data))
-            });
-
-        // Wrap in a stream that decrements on drop
-        let counted_stream = CountedStream {
-            inner: stream,
-            counter,
-        };
-
-        Some(
-            Sse::new(counted_stream)
-                .keep_alive(KeepAlive::new().interval(Duration::from_secs(30)).text("")),
-        )
+                Delivery::Event(_) => None,
+                Delivery::Resync { after } => Some(Ok(Event::default()
+                    .event("resync")
+                    .data(serde_json::json!({ "after": after }).to_string()))),
+            }
+        }))
     }
 }
 
@@ -143,6 +353,82 @@ impl Default for SseManager {
     }
 }
 
+/// Keep-alive used by every SSE endpoint on this manager.
+pub fn keep_alive() -> KeepAlive {
+    KeepAlive::new().interval(Duration::from_secs(30)).text("")
+}
+
+/// SSE `event:` name of `event`.
+fn event_name(event: &SseEvent) -> &'static str {
+    match event {
+        SseEvent::Response { .. } => "response",
+        SseEvent::Thinking { .. } => "thinking",
+        SseEvent::ToolStarted { .. } => "tool_started",
+        SseEvent::ToolCompleted { .. } => "tool_completed",
+        SseEvent::ToolResult { .. } => "tool_result",
+        SseEvent::StreamChunk { .. } => "stream_chunk",
+        SseEvent::Status { .. } => "status",
+        SseEvent::ApprovalNeeded { .. } => "approval_needed",
+        SseEvent::AuthRequired { .. } => "auth_required",
+        SseEvent::AuthCompleted { .. } => "auth_completed",
+        SseEvent::Error { .. } => "error",
+        SseEvent::JobStarted { .. } => "job_started",
+        SseEvent::JobMessage { .. } => "job_message",
+        SseEvent::JobToolUse { .. } => "job_tool_use",
+        SseEvent::JobToolResult { .. } => "job_tool_result",
+        SseEvent::JobStatus { .. } => "job_status",
+        SseEvent::JobResult { .. } => "job_result",
+        SseEvent::MatterNote { .. } => "matter_note",
+        SseEvent::MatterNoteMention { .. } => "matter_note_mention",
+        SseEvent::Domain { .. } => "domain_event",
+        SseEvent::Notification(_) => "notification",
+        SseEvent::Heartbeat => "heartbeat",
+        SseEvent::Typing { .. } => "typing",
+    }
+}
+
+/// Events that only matter to connections that are live when they happen.
+fn is_ephemeral(event: &SseEvent) -> bool {
+    matches!(event, SseEvent::Heartbeat | SseEvent::Typing { .. })
+}
+
+/// One connection's position in the event sequence.
+struct Cursor {
+    rx: broadcast::Receiver<SequencedEvent>,
+    replay: Arc<Mutex<ReplayBuffer>>,
+    /// Id of the newest event delivered (or skipped as already seen).
+    last_id: u64,
+    /// Replayed deliveries waiting to be sent.
+    pending: VecDeque<Delivery>,
+}
+
+impl Cursor {
+    async fn next(&mut self) -> Option<Delivery> {
+        loop {
+            if let Some(delivery) = self.pending.pop_front() {
+                if let Delivery::Event(ref sequenced) = delivery {
+                    self.last_id = self.last_id.max(sequenced.id);
+                }
+                return Some(delivery);
+            }
+            match self.rx.recv().await {
+                // Already delivered from the replay buffer.
+                Ok(sequenced) if sequenced.id <= self.last_id => continue,
+                Ok(sequenced) => {
+                    self.last_id = sequenced.id;
+                    return Some(Delivery::Event(Box::new(sequenced)));
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "SSE connection lagged; replaying missed events");
+                    let replay = self.replay.lock().unwrap_or_else(|e| e.into_inner());
+                    self.pending = replay.since(self.last_id).into();
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
 /// Stream wrapper that decrements connection count on drop.
 ///
 /// When the SSE client disconnects, this stream is dropped
@@ -189,22 +475,119 @@ mod tests {
     #[tokio::test]
     async fn test_broadcast_to_receiver() {
         let manager = SseManager::new();
-        let mut rx = BroadcastStream::new(manager.tx.subscribe());
+        let mut rx = manager.tx.subscribe();
 
         manager.broadcast(SseEvent::Status {
             message: "test".to_string(),
             thread_id: None,
         });
 
-        let event = rx.next().await;
-        assert!(event.is_some());
-        let event = event.unwrap().unwrap();
-        match event {
+        let sequenced = rx.recv().await.unwrap();
+        match sequenced.event {
             SseEvent::Status { message, .. } => assert_eq!(message, "test"),
             _ => panic!("unexpected event type"),
         }
     }
 
+    fn status(message: &str) -> SseEvent {
+        SseEvent::Status {
+            message: message.to_string(),
+            thread_id: None,
+        }
+    }
+
+    fn delivered_messages(deliveries: &[Delivery]) -> Vec<String> {
+        deliveries
+            .iter()
+            .map(|delivery| match delivery {
+                Delivery::Event(sequenced) => match &sequenced.event {
+                    SseEvent::Status { message, .. } => message.clone(),
+                    other => format!("{other:?}"),
+                },
+                Delivery::Resync { .. } => "resync".to_string(),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_resume_replays_events_after_last_event_id() {
+        let manager = SseManager::new();
+        let mut first = Box::pin(manager.subscribe_deliveries(None).unwrap());
+        manager.broadcast(status("one"));
+        let Some(Delivery::Event(seen)) = first.next().await else {
+            panic!("expected event");
+        };
+        drop(first);
+
+        // Missed while disconnected.
+        manager.broadcast(status("two"));
+        manager.broadcast(SseEvent::Heartbeat);
+        manager.broadcast(status("three"));
+
+        let mut resumed = Box::pin(manager.subscribe_deliveries(Some(seen.id)).unwrap());
+        manager.broadcast(status("four"));
+        let mut deliveries = Vec::new();
+        for _ in 0..3 {
+            deliveries.push(resumed.next().await.unwrap());
+        }
+        assert_eq!(delivered_messages(&deliveries), ["two", "three", "four"]);
+
+        // An id from before the buffer (e.g. a previous server process)
+        // gets a resync marker before what is still buffered.
+        let stale = Box::pin(manager.subscribe_deliveries(Some(1)).unwrap());
+        let deliveries: Vec<_> = stale.take(5).collect().await;
+        assert_eq!(
+            delivered_messages(&deliveries),
+            ["resync", "one", "two", "three", "four"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lagging_connection_catches_up_from_replay_buffer() {
+        let manager = SseManager::new();
+        let mut slow = Box::pin(manager.subscribe_deliveries(None).unwrap());
+        let sent = CONNECTION_BUFFER + 10;
+        for i in 0..sent {
+            manager.broadcast(status(&i.to_string()));
+        }
+        let deliveries: Vec<_> = slow.as_mut().take(sent).collect().await;
+        let expected: Vec<String> = (0..sent).map(|i| i.to_string()).collect();
+        assert_eq!(delivered_messages(&deliveries), expected);
+
+        // Past the replay buffer the client is told to resync.
+        for i in 0..REPLAY_CAPACITY + 10 {
+            manager.broadcast(status(&i.to_string()));
+        }
+        assert!(matches!(slow.next().await, Some(Delivery::Resync { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_topic_filter_and_event_ids() {
+        let topics = SseTopics::parse("jobs, matters").unwrap();
+        assert!(!topics.allows(&status("chat")));
+        assert!(topics.allows(&SseEvent::JobStatus {
+            job_id: "j1".to_string(),
+            message: "running".to_string(),
+        }));
+        assert!(topics.allows(&SseEvent::Heartbeat));
+        assert!(SseTopics::parse("chat,billing").is_err());
+        assert!(!SseTopics::default().logs);
+
+        let manager = SseManager::new();
+        let mut chat_only = Box::pin(
+            manager
+                .subscribe_events(None, SseTopics::parse("chat").unwrap())
+                .unwrap(),
+        );
+        manager.broadcast(SseEvent::JobStatus {
+            job_id: "j1".to_string(),
+            message: "running".to_string(),
+        });
+        manager.broadcast(status("hello"));
+        assert!(chat_only.next().await.is_some());
+        assert_eq!(manager.connection_count(), 1);
+    }
+
     #[tokio::test]
     async fn test_subscribe_raw_receives_events() {
         let manager = SseManager::new();
@@ -266,6 +649,10 @@ mod tests {
 
         // Third should be rejected
         assert!(manager.subscribe_raw().is_none());
-        assert!(manager.subscribe().is_none());
+        assert!(
+            manager
+                .subscribe_events(None, SseTopics::default())
+                .is_none()
+        );
     }
 }
@@ -1,15 +1,19 @@
 //! Shared web gateway runtime state types.
 
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
 
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{broadcast, mpsc, oneshot};
 
 use crate::agent::SessionManager;
-use crate::channels::IncomingMessage;
+use crate::channels::web::handlers::helpers::legal::record_legal_audit_event;
+use crate::channels::web::handlers::notifications::notification_to_info;
 use crate::channels::web::log_layer::LogBroadcaster;
+use crate::channels::web::rate_limit::KeyedRateLimiter;
 use crate::channels::web::sse::SseManager;
-use crate::db::Database;
+use crate::channels::web::types::SseEvent;
+use crate::channels::{IncomingMessage, IntakeError, IntakeQueue};
+use crate::db::{AuditSeverity, Database};
+use crate::events::DomainEvent;
 use crate::extensions::ExtensionManager;
 use crate::orchestrator::job_manager::ContainerJobManager;
 use crate::tools::ToolRegistry;
@@ -25,73 +29,12 @@ pub type PromptQueue = Arc<
     >,
 >;
 
-/// Simple sliding-window rate limiter.
-///
-/// Tracks the number of requests in the current window. Resets when the window expires.
-/// Not per-IP (since this is a single-user gateway with auth), but prevents flooding.
-pub struct RateLimiter {
-    /// Requests remaining in the current window.
-    remaining: AtomicU64,
-    /// Epoch second when the current window started.
-    window_start: AtomicU64,
-    /// Maximum requests per window.
-    max_requests: u64,
-    /// Window duration in seconds.
-    window_secs: u64,
-}
-
-impl RateLimiter {
-    pub fn new(max_requests: u64, window_secs: u64) -> Self {
-        Self {
-            remaining: AtomicU64::new(max_requests),
-            window_start: AtomicU64::new(
-                std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_secs(),
-            ),
-            max_requests,
-            window_secs,
-        }
-    }
-
-    /// Try to consume one request. Returns `true` if allowed, `false` if rate limited.
-    pub fn check(&self) -> bool {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-
-        let window = self.window_start.load(Ordering::Relaxed);
-        if now.saturating_sub(window) >= self.window_secs {
-            // Window expired, reset
-            self.window_start.store(now, Ordering::Relaxed);
-            self.remaining
-                .store(self.max_requests - 1, Ordering::Relaxed);
-            return true;
-        }
-
-        // Try to decrement remaining
-        loop {
-            let current = self.remaining.load(Ordering::Relaxed);
-            if current == 0 {
-                return false;
-            }
-            if self
-                .remaining
-                .compare_exchange_weak(current, current - 1, Ordering::Relaxed, Ordering::Relaxed)
-                .is_ok()
-            {
-                return true;
-            }
-        }
-    }
-}
-
 /// Shared state for all gateway handlers.
 pub struct GatewayState {
     /// Channel to send messages to the agent loop.
     pub msg_tx: tokio::sync::RwLock<Option<mpsc::Sender<IncomingMessage>>>,
+    /// Admission control and depth metrics for `msg_tx`.
+    pub intake: Arc<IntakeQueue>,
     /// SSE broadcast manager.
     pub sse: SseManager,
     /// Workspace for memory API.
@@ -124,17 +67,115 @@ pub struct GatewayState {
     pub skill_registry: Option<Arc<std::sync::RwLock<crate::skills::SkillRegistry>>>,
     /// Skill catalog for searching the ClawHub registry.
     pub skill_catalog: Option<Arc<crate::skills::catalog::SkillCatalog>>,
-    /// Rate limiter for chat endpoints (30 messages per 60 seconds).
-    pub chat_rate_limiter: RateLimiter,
+    /// Per-user, per-route-group request limits applied by
+    /// [`rate_limit_middleware`](crate::channels::web::rate_limit::rate_limit_middleware).
+    pub rate_limiter: Arc<KeyedRateLimiter>,
     /// Registry catalog entries for the available extensions API.
     /// Populated at startup from `registry/` manifests, independent of extension manager.
     pub registry_entries: Vec<crate::extensions::RegistryEntry>,
     /// Cost guard for token/cost tracking.
     pub cost_guard: Option<Arc<crate::agent::cost_guard::CostGuard>>,
+    /// Supervised-mode review queue for held outbound messages.
+    pub review_queue: Option<Arc<crate::channels::ReviewQueue>>,
+    /// Routine engine handle for inbound webhook triggers.
+    pub routine_engine: Option<crate::agent::RoutineEngineSlot>,
+    /// Outbound webhook dispatcher for firm integrations.
+    pub webhooks: Option<Arc<crate::integrations::WebhookDispatcher>>,
+    /// Git mirror of the workspace, when configured.
+    pub git_mirror: Option<Arc<crate::workspace::git_mirror::GitMirror>>,
+    /// Domain event bus handlers publish to.
+    pub events: crate::events::EventBus,
     /// Server startup time for uptime calculation.
     pub startup_time: std::time::Instant,
     /// Legal config for legal-policy-aware web endpoints.
     pub legal_config: Option<crate::config::LegalConfig>,
     /// Snapshot of runtime facts used for compliance scoring.
     pub runtime_facts: crate::compliance::ComplianceRuntimeFacts,
+    /// HMAC key for precedent anonymization review tokens; see
+    /// [`derive_review_token_key`](crate::channels::web::auth::derive_review_token_key).
+    pub review_token_key: Vec<u8>,
+}
+
+impl GatewayState {
+    /// Admit a message into the agent loop without waiting, applying the
+    /// intake queue's capacity and per-user limits.
+    pub async fn enqueue_message(&self, msg: IncomingMessage) -> Result<(), IntakeError> {
+        let tx = self.msg_tx.read().await;
+        self.intake.submit(tx.as_ref(), msg)
+    }
+
+    /// Publish a domain event to every subscriber on the bus.
+    pub fn publish_event(&self, event: DomainEvent) {
+        self.events.publish(event);
+    }
+
+    /// Subscribe the gateway's consumers to the event bus: the SSE/WebSocket
+    /// stream, outbound webhooks, and the audit log for events the publishing
+    /// handler does not already audit. Inbox notifications are forwarded to
+    /// the stream as well.
+    pub fn spawn_event_subscribers(self: &Arc<Self>) {
+        let state = Arc::clone(self);
+        self.events.spawn_subscriber("gateway", move |event| {
+            let state = Arc::clone(&state);
+            async move {
+                state.sse.broadcast(SseEvent::Domain {
+                    event: event.name().to_string(),
+                    matter_id: event.matter_id().map(str::to_string),
+                    data: event.payload(),
+                });
+                audit_domain_event(&state, &event).await;
+            }
+        });
+        if let Some(webhooks) = self.webhooks.as_ref() {
+            webhooks.subscribe(&self.events);
+        }
+        self.spawn_notification_forwarder();
+    }
+
+    /// Push new inbox notifications for the gateway's user to SSE/WebSocket
+    /// clients.
+    fn spawn_notification_forwarder(self: &Arc<Self>) {
+        let state = Arc::clone(self);
+        let mut rx = crate::notifications::subscribe();
+        tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(record) if record.user_id == state.user_id => {
+                        state
+                            .sse
+                            .broadcast(SseEvent::Notification(notification_to_info(record)));
+                    }
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(
+                            skipped,
+                            "Notification push lagged; clients will catch up on refresh"
+                        );
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+}
+
+/// Matter creation and conflict hits are audited synchronously by their
+/// handlers; added deadlines, generated documents, and finished jobs are
+/// audited here.
+async fn audit_domain_event(state: &GatewayState, event: &DomainEvent) {
+    let (event_type, actor) = match event {
+        DomainEvent::DeadlineAdded { actor, .. } => ("deadline_added", actor.as_str()),
+        DomainEvent::DocumentGenerated { actor, .. } => ("document_generated", actor.as_str()),
+        DomainEvent::JobCompleted { .. } => ("job_completed", state.user_id.as_str()),
+        DomainEvent::MatterCreated { .. } | DomainEvent::ConflictHit { .. } => return,
+    };
+    record_legal_audit_event(
+        state,
+        event_type,
+        actor,
+        event.matter_id(),
+        AuditSeverity::Info,
+        event.payload(),
+    )
+    .await;
 }
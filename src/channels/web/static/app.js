@@ -9,10 +9,7 @@ let assistantThreadId = null;
 let hasMore = false;
 let oldestTimestamp = null;
 let loadingOlder = false;
-let sseHasConnectedBefore = false;
-let jobEvents = new Map(); // job_id -> Array of events
 let jobListRefreshTimer = null;
-const JOB_EVENTS_CAP = 500;
 const MEMORY_SEARCH_QUERY_MAX_LENGTH = 100;
 let lastBackupId = null;
 const PRIMARY_TABS = ['chat', 'matters', 'memory', 'jobs'];
@@ -115,6 +112,7 @@ function authenticate() {
       connectSSE();
       connectLogSSE();
       startGatewayStatusPolling();
+      loadNotifications();
       checkTeeStatus();
       loadThreads();
       loadMemoryTree();
@@ -397,14 +395,11 @@ function connectSSE() {
 
   eventSource = new EventSource('/api/chat/events?token=' + encodeURIComponent(token));
 
+  // EventSource reconnects with Last-Event-ID and the server replays what
+  // was missed, so a reconnect only reloads state when told to resync.
   eventSource.onopen = () => {
     document.getElementById('sse-dot').classList.remove('disconnected');
     document.getElementById('sse-status').textContent = 'Connected';
-    if (sseHasConnectedBefore && currentThreadId) {
-      finalizeActivityGroup();
-      loadHistory();
-    }
-    sseHasConnectedBefore = true;
   };
 
   eventSource.onerror = () => {
@@ -416,7 +411,7 @@ function connectSSE() {
     const data = JSON.parse(e.data);
     if (!isCurrentThread(data.thread_id)) return;
     finalizeActivityGroup();
-    addMessage('assistant', data.content);
+    addMessage('assistant', data.content, data.citations);
     setStatus('');
     enableChatInput();
     // Refresh thread list so new titles appear after first message
@@ -500,32 +495,48 @@ function connectSSE() {
     }
   });
 
-  // Job event listeners (activity stream for all sandbox jobs)
-  const jobEventTypes = [
-    'job_message', 'job_tool_use', 'job_tool_result',
-    'job_status', 'job_result'
-  ];
-  for (const evtType of jobEventTypes) {
+  // Live matter notes: refresh the open matter's workstream on change
+  eventSource.addEventListener('matter_note', (e) => {
+    const data = JSON.parse(e.data);
+    if (data.matter_id !== selectedMatterId || !currentMatterWork.loaded) return;
+    loadMatterWorkDataIfNeeded(true);
+  });
+
+  eventSource.addEventListener('matter_note_mention', (e) => {
+    const data = JSON.parse(e.data);
+    showToast(
+      data.author + ' mentioned @' + data.mentioned_user_id + ' on ' + data.matter_id,
+      'info'
+    );
+  });
+
+  // Missed events are no longer buffered (long outage or server restart).
+  eventSource.addEventListener('resync', () => {
+    if (currentThreadId) {
+      finalizeActivityGroup();
+      loadHistory();
+    }
+    loadNotifications();
+  });
+
+  // Inbox notifications (routine output, deadline alerts, system warnings)
+  eventSource.addEventListener('notification', (e) => {
+    const data = JSON.parse(e.data);
+    showToast(data.title, data.severity === 'info' ? 'info' : 'error');
+    loadNotifications();
+  });
+
+  // Job status changes refresh the job list; per-job activity streams from
+  // /api/jobs/{id}/logs/stream.
+  for (const evtType of ['job_status', 'job_result']) {
     eventSource.addEventListener(evtType, (e) => {
       const data = JSON.parse(e.data);
-      const jobId = data.job_id;
-      if (!jobId) return;
-      if (!jobEvents.has(jobId)) jobEvents.set(jobId, []);
-      const events = jobEvents.get(jobId);
-      events.push({ type: evtType, data: data, ts: Date.now() });
-      // Cap per-job events to prevent memory leak
-      while (events.length > JOB_EVENTS_CAP) events.shift();
-      // If the Activity tab is currently visible for this job, refresh it
-      refreshActivityTab(jobId);
+      if (!data.job_id) return;
       // Auto-refresh job list when on jobs tab (debounced)
-      if ((evtType === 'job_result' || evtType === 'job_status') && currentTab === 'jobs' && !currentJobId) {
+      if (currentTab === 'jobs' && !currentJobId) {
         clearTimeout(jobListRefreshTimer);
         jobListRefreshTimer = setTimeout(loadJobs, 200);
       }
-      // Clean up finished job events after a viewing window
-      if (evtType === 'job_result') {
-        setTimeout(() => jobEvents.delete(jobId), 60000);
-      }
     });
   }
 }
@@ -611,9 +622,48 @@ function renderMarkdown(text) {
       /<pre>/g,
       '<pre class="code-block-wrapper"><button class="copy-btn" type="button" data-copy-code="1">Copy</button>'
     );
-    return html;
+    return renderChunkCitations(html);
   }
-  return escapeHtml(text);
+  return renderChunkCitations(escapeHtml(text));
+}
+
+// Turn [[chunk:<uuid>]] anchors from memory_search into numbered citation
+// links. Repeated anchors reuse their first number.
+const CHUNK_ANCHOR_RE = /\[\[chunk:([0-9a-fA-F-]{36})\]\]/g;
+
+function renderChunkCitations(html) {
+  const numbers = {};
+  let next = 1;
+  return html.replace(CHUNK_ANCHOR_RE, (match, id) => {
+    const key = id.toLowerCase();
+    if (!numbers[key]) numbers[key] = next++;
+    return '<a href="#" class="chunk-citation" data-chunk-id="' + key + '">[' + numbers[key] + ']</a>';
+  });
+}
+
+// Label resolved citations with their source path and flag the rest as stale.
+function annotateChunkCitations(el, citations) {
+  if (!Array.isArray(citations)) return;
+  const paths = {};
+  citations.forEach((c) => { paths[c.chunk_id] = c.path; });
+  el.querySelectorAll('a.chunk-citation').forEach((link) => {
+    const path = paths[link.getAttribute('data-chunk-id')];
+    if (path) {
+      link.title = path;
+    } else {
+      link.classList.add('stale');
+      link.title = 'Source passage no longer indexed';
+    }
+  });
+}
+
+function openChunkCitation(chunkId) {
+  apiFetch('/api/memory/chunks/' + encodeURIComponent(chunkId)).then((chunk) => {
+    switchTab('memory');
+    readMemoryFile(chunk.path, chunk.passage || chunk.content);
+  }).catch((err) => {
+    showToast('Citation unavailable: ' + err.message, 'error');
+  });
 }
 
 // Strip dangerous HTML elements and attributes from rendered markdown.
@@ -648,7 +698,7 @@ function copyCodeBlock(btn) {
   });
 }
 
-function addMessage(role, content) {
+function addMessage(role, content, citations) {
   const container = document.getElementById('chat-messages');
   const div = document.createElement('div');
   div.className = 'message ' + role;
@@ -657,6 +707,7 @@ function addMessage(role, content) {
   } else {
     div.setAttribute('data-raw', content);
     div.innerHTML = renderMarkdown(content);
+    annotateChunkCitations(div, citations);
   }
   container.appendChild(div);
   container.scrollTop = container.scrollHeight;
@@ -1345,6 +1396,11 @@ delegate(byId('chat-messages'), 'click', 'button[data-copy-code]', function(even
   copyCodeBlock(button);
 });
 
+delegate(byId('chat-messages'), 'click', 'a.chunk-citation', function(event, link) {
+  event.preventDefault();
+  openChunkCitation(link.getAttribute('data-chunk-id'));
+});
+
 function autoResizeTextarea(el) {
   el.style.height = 'auto';
   el.style.height = Math.min(el.scrollHeight, 120) + 'px';
@@ -1605,7 +1661,9 @@ function toggleExpand(node) {
   }).catch(() => {});
 }
 
-function readMemoryFile(path) {
+// `highlight` is a passage to mark and scroll to (from a chunk citation);
+// the file is shown as plain text so the passage can be located exactly.
+function readMemoryFile(path, highlight) {
   const requestVersion = beginRequest('memoryRead');
   beginRequest('memoryDirectory');
   currentMemoryPath = path;
@@ -1620,8 +1678,16 @@ function readMemoryFile(path) {
     if (!isCurrentRequest('memoryRead', requestVersion)) return;
     currentMemoryContent = data.content;
     const viewer = document.getElementById('memory-viewer');
-    // Render markdown if it's a .md file
-    if (path.endsWith('.md')) {
+    const at = highlight ? data.content.indexOf(highlight) : -1;
+    if (at >= 0) {
+      viewer.innerHTML = escapeHtml(data.content.slice(0, at))
+        + '<mark class="chunk-highlight">' + escapeHtml(highlight) + '</mark>'
+        + escapeHtml(data.content.slice(at + highlight.length));
+      viewer.classList.remove('rendered');
+      const mark = viewer.querySelector('mark.chunk-highlight');
+      if (mark) mark.scrollIntoView({ block: 'center' });
+    } else if (path.endsWith('.md')) {
+      // Render markdown if it's a .md file
       viewer.innerHTML = '<div class="memory-rendered">' + renderMarkdown(data.content) + '</div>';
       viewer.classList.add('rendered');
     } else {
@@ -2546,7 +2612,7 @@ function approvePairing(channel, code, container) {
 
 let currentJobId = null;
 let currentJobSubTab = 'overview';
-let jobFilesTreeState = null;
+let jobArtifactsState = null;
 
 delegate(byId('jobs-tbody'), 'click', 'button[data-job-action]', function(event, button) {
   event.preventDefault();
@@ -2585,7 +2651,7 @@ function loadJobs() {
   const requestVersion = beginRequest('jobsList');
   beginRequest('jobDetail');
   currentJobId = null;
-  jobFilesTreeState = null;
+  jobArtifactsState = null;
 
   // Rebuild DOM if renderJobDetail() destroyed it (it wipes .jobs-container innerHTML).
   const container = document.querySelector('.jobs-container');
@@ -2699,8 +2765,9 @@ function openJobDetail(jobId) {
 }
 
 function closeJobDetail() {
+  closeActivityStream();
   currentJobId = null;
-  jobFilesTreeState = null;
+  jobArtifactsState = null;
   loadJobs();
 }
 
@@ -2731,7 +2798,7 @@ function renderJobDetail(job) {
   // Sub-tab bar
   const tabs = document.createElement('div');
   tabs.className = 'job-detail-tabs';
-  const subtabs = ['overview', 'activity', 'files'];
+  const subtabs = ['overview', 'activity', 'artifacts'];
   for (const st of subtabs) {
     const btn = document.createElement('button');
     btn.textContent = st.charAt(0).toUpperCase() + st.slice(1);
@@ -2751,7 +2818,7 @@ function renderJobDetail(job) {
 
   switch (currentJobSubTab) {
     case 'overview': renderJobOverview(content, job); break;
-    case 'files': renderJobFiles(content, job); break;
+    case 'artifacts': renderJobArtifacts(content, job); break;
     case 'activity': renderJobActivity(content, job); break;
   }
 }
@@ -2830,84 +2897,53 @@ function renderJobOverview(container, job) {
   }
 }
 
-function renderJobFiles(container, job) {
+function renderJobArtifacts(container, job) {
   container.innerHTML = '<div class="job-files">'
     + '<div class="job-files-sidebar"><div class="job-files-tree"></div></div>'
-    + '<div class="job-files-viewer"><div class="empty-state">Select a file to view</div></div>'
+    + '<div class="job-files-viewer"><div class="empty-state">Select an artifact</div></div>'
     + '</div>';
 
   container._jobId = job ? job.id : null;
 
-  apiFetch('/api/jobs/' + job.id + '/files/list?path=').then((data) => {
-    jobFilesTreeState = data.entries.map((e) => ({
-      name: e.name,
-      path: e.path,
-      is_dir: e.is_dir,
-      children: e.is_dir ? null : undefined,
-      expanded: false,
-      loaded: false,
-    }));
-    renderJobFilesTree();
+  apiFetch('/api/jobs/' + job.id + '/artifacts').then((data) => {
+    jobArtifactsState = data.artifacts || [];
+    renderJobArtifactList();
   }).catch(() => {
-    const treeContainer = document.querySelector('.job-files-tree');
-    if (treeContainer) {
-      treeContainer.innerHTML = '<div class="tree-item">No project files</div>';
+    const listContainer = document.querySelector('.job-files-tree');
+    if (listContainer) {
+      listContainer.innerHTML = '<div class="tree-item">No artifacts</div>';
     }
   });
 }
 
-function renderJobFilesTree() {
-  const treeContainer = document.querySelector('.job-files-tree');
-  if (!treeContainer) return;
-  treeContainer.innerHTML = '';
-  if (!jobFilesTreeState || jobFilesTreeState.length === 0) {
-    treeContainer.innerHTML = '<div class="tree-item">No files in workspace</div>';
-    return;
-  }
-  renderJobFileNodes(jobFilesTreeState, treeContainer, 0);
+function formatArtifactSize(bytes) {
+  if (bytes < 1024) return bytes + ' B';
+  if (bytes < 1024 * 1024) return (bytes / 1024).toFixed(1) + ' KB';
+  return (bytes / (1024 * 1024)).toFixed(1) + ' MB';
 }
 
-function renderJobFileNodes(nodes, container, depth) {
-  for (const node of nodes) {
+function jobArtifactUrl(jobId, name) {
+  return '/api/jobs/' + encodeURIComponent(jobId) + '/artifacts/'
+    + name.split('/').map(encodeURIComponent).join('/');
+}
+
+function renderJobArtifactList() {
+  const listContainer = document.querySelector('.job-files-tree');
+  if (!listContainer) return;
+  listContainer.innerHTML = '';
+  if (!jobArtifactsState || jobArtifactsState.length === 0) {
+    listContainer.innerHTML = '<div class="tree-item">No artifacts yet. Files are collected when the job finishes.</div>';
+    return;
+  }
+  for (const artifact of jobArtifactsState) {
     const row = document.createElement('div');
     row.className = 'tree-row';
-    row.style.paddingLeft = (depth * 16 + 8) + 'px';
-
-    if (node.is_dir) {
-      const arrow = document.createElement('span');
-      arrow.className = 'expand-arrow' + (node.expanded ? ' expanded' : '');
-      arrow.textContent = '\u25B6';
-      arrow.addEventListener('click', (e) => {
-        e.stopPropagation();
-        toggleJobFileExpand(node);
-      });
-      row.appendChild(arrow);
-
-      const label = document.createElement('span');
-      label.className = 'tree-label dir';
-      label.textContent = node.name;
-      label.addEventListener('click', () => toggleJobFileExpand(node));
-      row.appendChild(label);
-    } else {
-      const spacer = document.createElement('span');
-      spacer.className = 'expand-arrow-spacer';
-      row.appendChild(spacer);
-
-      const label = document.createElement('span');
-      label.className = 'tree-label file';
-      label.textContent = node.name;
-      label.addEventListener('click', () => readJobFile(node.path));
-      row.appendChild(label);
-    }
-
-    container.appendChild(row);
-
-    if (node.is_dir && node.expanded && node.children) {
-      const childContainer = document.createElement('div');
-      childContainer.className = 'tree-children';
-      renderJobFileNodes(node.children, childContainer, depth + 1);
-      container.appendChild(childContainer);
-    }
+    const label = document.createElement('span');
+    label.className = 'tree-label file';
+    label.textContent = artifact.name + ' (' + formatArtifactSize(artifact.size_bytes) + ')';
+    label.addEventListener('click', () => showJobArtifact(artifact));
+    row.appendChild(label);
+    listContainer.appendChild(row);
   }
 }
 
@@ -2916,55 +2952,62 @@ function getJobId() {
   return (container && container._jobId) || null;
 }
 
-function toggleJobFileExpand(node) {
-  if (node.expanded) {
-    node.expanded = false;
-    renderJobFilesTree();
-    return;
-  }
-  if (node.loaded) {
-    node.expanded = true;
-    renderJobFilesTree();
-    return;
-  }
-  const jobId = getJobId();
-  apiFetch('/api/jobs/' + jobId + '/files/list?path=' + encodeURIComponent(node.path)).then((data) => {
-    node.children = data.entries.map((e) => ({
-      name: e.name,
-      path: e.path,
-      is_dir: e.is_dir,
-      children: e.is_dir ? null : undefined,
-      expanded: false,
-      loaded: false,
-    }));
-    node.loaded = true;
-    node.expanded = true;
-    renderJobFilesTree();
-  }).catch(() => {});
-}
-
-function readJobFile(path) {
+function showJobArtifact(artifact) {
   const viewer = document.querySelector('.job-files-viewer');
   if (!viewer) return;
   const jobId = getJobId();
-  apiFetch('/api/jobs/' + jobId + '/files/read?path=' + encodeURIComponent(path)).then((data) => {
-    viewer.innerHTML = '<div class="job-files-path">' + escapeHtml(path) + '</div>'
-      + '<pre class="job-files-content">' + escapeHtml(data.content) + '</pre>';
+  const url = jobArtifactUrl(jobId, artifact.name);
+  const isText = artifact.content_type.startsWith('text/') || artifact.content_type === 'application/json';
+
+  viewer.innerHTML = '<div class="job-files-path">' + escapeHtml(artifact.name)
+    + ' &middot; ' + escapeHtml(artifact.content_type)
+    + ' &middot; <a href="' + escapeHtml(url + '?token=' + encodeURIComponent(token)) + '" download>Download</a>'
+    + (isText ? ' &middot; <button type="button" class="btn-promote-artifact">Promote to matter</button>' : '')
+    + (artifact.promoted_to ? '<div class="job-files-promoted">Promoted to ' + escapeHtml(artifact.promoted_to) + '</div>' : '')
+    + '</div>'
+    + (isText ? '<pre class="job-files-content">Loading...</pre>' : '<div class="empty-state">No preview for this file type</div>');
+
+  const promoteBtn = viewer.querySelector('.btn-promote-artifact');
+  if (promoteBtn) {
+    promoteBtn.addEventListener('click', () => promoteJobArtifact(jobId, artifact));
+  }
+  if (!isText) return;
+  apiFetch(url).then((body) => {
+    const pre = viewer.querySelector('.job-files-content');
+    if (pre) pre.textContent = typeof body === 'string' ? body : JSON.stringify(body, null, 2);
   }).catch((err) => {
     viewer.innerHTML = '<div class="empty-state">Error: ' + escapeHtml(err.message) + '</div>';
   });
 }
 
+function promoteJobArtifact(jobId, artifact) {
+  const matterId = window.prompt('Promote "' + artifact.name + '" into which matter?', activeMatterId || '');
+  if (!matterId) return;
+  apiFetch(jobArtifactUrl(jobId, artifact.name), {
+    method: 'POST',
+    body: { matter_id: matterId.trim() },
+  }).then((data) => {
+    artifact.promoted_to = data.document.path;
+    showToast('Saved to ' + data.document.path, 'success');
+    showJobArtifact(artifact);
+  }).catch((err) => {
+    showToast('Promote failed: ' + err.message, 'error');
+  });
+}
+
 // --- Activity tab (unified for all sandbox jobs) ---
 
-let activityCurrentJobId = null;
-// Track how many live SSE events we've already rendered so refreshActivityTab
-// only appends new ones (avoids duplicates on each SSE tick).
-let activityRenderedLiveIndex = 0;
+let activityStream = null;
+
+function closeActivityStream() {
+  if (activityStream) {
+    activityStream.close();
+    activityStream = null;
+  }
+}
 
 function renderJobActivity(container, job) {
-  activityCurrentJobId = job ? job.id : null;
-  activityRenderedLiveIndex = 0;
+  closeActivityStream();
 
   container.innerHTML = '<div class="activity-toolbar">'
     + '<select id="activity-type-filter">'
@@ -2972,6 +3015,7 @@ function renderJobActivity(container, job) {
     + '<option value="message">Messages</option>'
     + '<option value="tool_use">Tool Calls</option>'
     + '<option value="tool_result">Results</option>'
+    + '<option value="log">Container Output</option>'
     + '</select>'
     + '<label class="logs-checkbox"><input type="checkbox" id="activity-autoscroll" checked> Auto-scroll</label>'
     + '</div>'
@@ -2995,30 +3039,24 @@ function renderJobActivity(container, job) {
     if (e.key === 'Enter') sendJobPrompt(job.id, false);
   });
 
-  // Load persisted events from DB, then catch up with any live SSE events
-  apiFetch('/api/jobs/' + job.id + '/events').then((data) => {
-    if (data.events && data.events.length > 0) {
-      for (const evt of data.events) {
-        appendActivityEvent(terminal, evt.event_type, evt.data);
-      }
+  // Persisted events replay first; the browser resumes from Last-Event-ID on reconnect.
+  const stream = new EventSource('/api/jobs/' + encodeURIComponent(job.id) + '/logs/stream?token=' + encodeURIComponent(token));
+  activityStream = stream;
+  stream.addEventListener('job_event', (e) => {
+    if (!terminal.isConnected) {
+      closeActivityStream();
+      return;
+    }
+    const evt = JSON.parse(e.data);
+    appendActivityEvent(terminal, evt.event_type, evt.data);
+    const autoScroll = document.getElementById('activity-autoscroll');
+    if (!autoScroll || autoScroll.checked) {
+      terminal.scrollTop = terminal.scrollHeight;
     }
-    appendNewLiveEvents(terminal, job.id);
-  }).catch(() => {
-    appendNewLiveEvents(terminal, job.id);
   });
-}
-
-function appendNewLiveEvents(terminal, jobId) {
-  const live = jobEvents.get(jobId) || [];
-  for (let i = activityRenderedLiveIndex; i < live.length; i++) {
-    const evt = live[i];
-    appendActivityEvent(terminal, evt.type.replace('job_', ''), evt.data);
-  }
-  activityRenderedLiveIndex = live.length;
-  const autoScroll = document.getElementById('activity-autoscroll');
-  if (!autoScroll || autoScroll.checked) {
-    terminal.scrollTop = terminal.scrollHeight;
-  }
+  stream.addEventListener('done', () => {
+    if (activityStream === stream) closeActivityStream();
+  });
 }
 
 function applyActivityFilter() {
@@ -3075,6 +3113,10 @@ function appendActivityEvent(terminal, eventType, data) {
     case 'status':
       el.innerHTML = '<span class="activity-status">' + escapeHtml(data.message || '') + '</span>';
       break;
+    case 'log':
+      el.innerHTML = '<span class="activity-log activity-log-' + escapeHtml(data.stream || 'stdout') + '">'
+        + escapeHtml(data.line || '') + '</span>';
+      break;
     case 'result':
       el.className += ' activity-final';
       const success = data.success !== false;
@@ -3091,14 +3133,6 @@ function appendActivityEvent(terminal, eventType, data) {
   terminal.appendChild(el);
 }
 
-function refreshActivityTab(jobId) {
-  if (activityCurrentJobId !== jobId) return;
-  if (currentJobSubTab !== 'activity') return;
-  const terminal = document.getElementById('activity-terminal');
-  if (!terminal) return;
-  appendNewLiveEvents(terminal, jobId);
-}
-
 function sendJobPrompt(jobId, done) {
   const input = document.getElementById('activity-prompt-input');
   const content = input ? input.value.trim() : '';
@@ -3416,6 +3450,15 @@ function fetchGatewayStatus() {
     html += '<div class="gw-stat"><span>WebSocket</span><span>' + (data.ws_connections || 0) + '</span></div>';
     html += '<div class="gw-stat"><span>Uptime</span><span>' + formatDuration(data.uptime_secs) + '</span></div>';
 
+    // Intake queue
+    if (data.intake) {
+      var rejected = (data.intake.rejected_queue_full || 0) + (data.intake.rejected_user_limit || 0);
+      html += '<div class="gw-divider"></div>';
+      html += '<div class="gw-section-label">Message Queue</div>';
+      html += '<div class="gw-stat"><span>Depth</span><span>' + (data.intake.depth || 0) + ' / ' + (data.intake.capacity || 0) + '</span></div>';
+      html += '<div class="gw-stat"><span>Rejected</span><span>' + rejected + '</span></div>';
+    }
+
     // Cost tracker
     if (data.daily_cost != null) {
       html += '<div class="gw-divider"></div>';
@@ -3461,6 +3504,87 @@ document.getElementById('gateway-status-trigger').addEventListener('mouseleave',
   document.getElementById('gateway-popover').classList.remove('visible');
 });
 
+// --- Notification inbox ---
+
+function loadNotifications() {
+  const requestVersion = beginRequest('notifications');
+  apiFetch('/api/notifications?limit=30').then(function(data) {
+    if (!isCurrentRequest('notifications', requestVersion)) return;
+    renderNotifications(data);
+  }).catch(function() {
+    if (!isCurrentRequest('notifications', requestVersion)) return;
+  });
+}
+
+function renderNotifications(data) {
+  var count = document.getElementById('notif-count');
+  count.textContent = data.unread_count > 99 ? '99+' : String(data.unread_count);
+  count.classList.toggle('is-hidden', !data.unread_count);
+
+  var list = document.getElementById('notif-list');
+  if (!data.notifications.length) {
+    list.innerHTML = '<div class="notif-empty">No notifications</div>';
+    return;
+  }
+  list.innerHTML = data.notifications.map(function(n) {
+    return '<div class="notif-item' + (n.read ? '' : ' unread') + ' severity-' + escapeHtml(n.severity) + '"'
+      + ' data-notification-id="' + escapeHtml(n.id) + '">'
+      + '<div class="notif-item-head">'
+      + '<span class="notif-kind">' + escapeHtml(n.kind) + '</span>'
+      + '<span class="notif-time">' + escapeHtml(formatRelativeTime(n.created_at)) + '</span>'
+      + '</div>'
+      + '<div class="notif-title">' + escapeHtml(n.title) + '</div>'
+      + (n.body ? '<div class="notif-body">' + escapeHtml(n.body) + '</div>' : '')
+      + (n.matter_id ? '<div class="notif-matter">Matter: ' + escapeHtml(n.matter_id) + '</div>' : '')
+      + '<div class="notif-actions">'
+      + '<button type="button" class="btn-link" data-notif-action="ack">Dismiss</button>'
+      + '<button type="button" class="btn-link" data-notif-action="snooze" data-minutes="60">Snooze 1h</button>'
+      + '<button type="button" class="btn-link" data-notif-action="snooze" data-minutes="1440">Tomorrow</button>'
+      + '</div>'
+      + '</div>';
+  }).join('');
+}
+
+function notificationAction(id, action, minutes) {
+  var path = '/api/notifications/' + encodeURIComponent(id) + '/' + action;
+  var options = { method: 'POST' };
+  if (action === 'snooze') options.body = { minutes: minutes };
+  apiFetch(path, options)
+    .then(loadNotifications)
+    .catch(function(err) { showToast('Notification update failed: ' + err.message, 'error'); });
+}
+
+document.getElementById('notif-bell-btn').addEventListener('click', function(e) {
+  e.stopPropagation();
+  var popover = document.getElementById('notif-popover');
+  popover.classList.toggle('visible');
+  if (popover.classList.contains('visible')) loadNotifications();
+});
+
+document.getElementById('notif-popover').addEventListener('click', function(e) {
+  e.stopPropagation();
+  var item = e.target.closest('[data-notification-id]');
+  if (!item) return;
+  var id = item.getAttribute('data-notification-id');
+  var button = e.target.closest('[data-notif-action]');
+  if (button) {
+    notificationAction(id, button.getAttribute('data-notif-action'), Number(button.getAttribute('data-minutes')));
+  } else if (item.classList.contains('unread')) {
+    notificationAction(id, 'read');
+  }
+});
+
+document.getElementById('notif-read-all').addEventListener('click', function(e) {
+  e.stopPropagation();
+  apiFetch('/api/notifications/read-all', { method: 'POST' })
+    .then(loadNotifications)
+    .catch(function(err) { showToast('Notification update failed: ' + err.message, 'error'); });
+});
+
+document.addEventListener('click', function() {
+  document.getElementById('notif-popover').classList.remove('visible');
+});
+
 // --- TEE attestation ---
 
 let teeInfo = null;
@@ -6785,15 +6909,32 @@ function openRoutineCreateModal() {
   var schedInput = document.createElement('input');
   schedInput.type = 'text';
   schedInput.className = 'configure-input';
-  schedInput.placeholder = '0 9 * * MON-FRI';
+  schedInput.placeholder = 'every weekday at 9am';
   var schedHint = document.createElement('div');
   schedHint.style.fontSize = '11px';
   schedHint.style.color = 'var(--text-secondary)';
   schedHint.style.marginTop = '4px';
-  schedHint.textContent = '6-field cron expression (second minute hour dom month dow)';
+  schedHint.textContent = 'Plain English (every weekday at 9am, every 2h) or cron (0 0 9 * * MON-FRI)';
   var cronField = makeField('Schedule', schedInput);
   cronField.appendChild(schedHint);
   cronDiv.appendChild(cronField);
+  var tzInput = document.createElement('input');
+  tzInput.type = 'text';
+  tzInput.className = 'configure-input';
+  tzInput.placeholder = 'UTC';
+  try {
+    tzInput.value = Intl.DateTimeFormat().resolvedOptions().timeZone || '';
+  } catch (e) {
+    tzInput.value = '';
+  }
+  cronDiv.appendChild(makeField('Timezone', tzInput, true));
+  var jitterInput = document.createElement('input');
+  jitterInput.type = 'number';
+  jitterInput.className = 'configure-input';
+  jitterInput.min = '0';
+  jitterInput.max = '3600';
+  jitterInput.value = '0';
+  cronDiv.appendChild(makeField('Jitter (seconds)', jitterInput, true));
   form.appendChild(cronDiv);
 
   var eventDiv = document.createElement('div');
@@ -6811,7 +6952,7 @@ function openRoutineCreateModal() {
 
   var actionSel = document.createElement('select');
   actionSel.className = 'configure-input';
-  ['lightweight', 'full_job'].forEach(function(v) {
+  ['lightweight', 'full_job', 'matter_status_report'].forEach(function(v) {
     var o = document.createElement('option');
     o.value = v;
     o.textContent = v;
@@ -6819,6 +6960,30 @@ function openRoutineCreateModal() {
   });
   form.appendChild(makeField('Action type', actionSel));
 
+  var reportDiv = document.createElement('div');
+  var reportMatterInput = document.createElement('input');
+  reportMatterInput.type = 'text';
+  reportMatterInput.className = 'configure-input';
+  reportMatterInput.placeholder = 'Leave blank for every active matter';
+  reportDiv.appendChild(makeField('Matter', reportMatterInput, true));
+  var reportPeriodInput = document.createElement('input');
+  reportPeriodInput.type = 'number';
+  reportPeriodInput.className = 'configure-input';
+  reportPeriodInput.value = '7';
+  reportPeriodInput.min = '1';
+  reportPeriodInput.max = '90';
+  reportDiv.appendChild(makeField('Period (days)', reportPeriodInput));
+  var reportSendLabel = document.createElement('label');
+  reportSendLabel.className = 'matter-action-checkbox';
+  var reportSendInput = document.createElement('input');
+  reportSendInput.type = 'checkbox';
+  reportSendLabel.appendChild(reportSendInput);
+  reportSendLabel.appendChild(
+    document.createTextNode(' Queue for attorney approval and email to the client')
+  );
+  reportDiv.appendChild(reportSendLabel);
+  form.appendChild(reportDiv);
+
   var promptLabel = document.createElement('label');
   promptLabel.textContent = 'Prompt';
   var promptInput = document.createElement('textarea');
@@ -6897,12 +7062,17 @@ function openRoutineCreateModal() {
   }
 
   function updateActionFields() {
-    promptLabel.textContent = actionSel.value === 'full_job' ? 'Description' : 'Prompt';
+    var action = actionSel.value;
+    promptLabel.textContent = action === 'full_job'
+      ? 'Description'
+      : (action === 'matter_status_report' ? 'Opening note (optional)' : 'Prompt');
+    reportDiv.style.display = action === 'matter_status_report' ? 'block' : 'none';
   }
 
   triggerSel.addEventListener('change', updateTriggerFields);
   actionSel.addEventListener('change', updateActionFields);
   updateTriggerFields();
+  updateActionFields();
   nameInput.focus();
 
   saveBtn.addEventListener('click', function() {
@@ -6930,8 +7100,9 @@ function openRoutineCreateModal() {
       return;
     }
 
+    var isReport = actionSel.value === 'matter_status_report';
     var prompt = promptInput.value.trim();
-    if (!prompt) {
+    if (!prompt && !isReport) {
       errMsg.textContent =
         (actionSel.value === 'full_job' ? 'Description' : 'Prompt') + ' is required.';
       errMsg.style.display = 'block';
@@ -6948,10 +7119,15 @@ function openRoutineCreateModal() {
       description: descInput.value.trim() || undefined,
       trigger_type: triggerType,
       schedule: triggerType === 'cron' ? schedInput.value.trim() : undefined,
+      timezone: (triggerType === 'cron' && tzInput.value.trim()) ? tzInput.value.trim() : undefined,
+      jitter_secs: triggerType === 'cron' ? Math.max(0, parseInt(jitterInput.value, 10) || 0) : undefined,
       event_pattern: triggerType === 'event' ? patternInput.value.trim() : undefined,
       event_channel: (triggerType === 'event' && channelInput.value.trim()) ? channelInput.value.trim() : undefined,
       action_type: actionSel.value,
       prompt: prompt,
+      matter_id: (isReport && reportMatterInput.value.trim()) ? reportMatterInput.value.trim() : undefined,
+      period_days: isReport ? (parseInt(reportPeriodInput.value, 10) || 7) : undefined,
+      send: isReport ? reportSendInput.checked : undefined,
       cooldown_secs: parsedCooldown,
     };
 
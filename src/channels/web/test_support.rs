@@ -7,7 +7,7 @@ use rust_decimal::Decimal;
 
 use crate::channels::web::auth::{AuthPrincipal, RequestPrincipal};
 use crate::channels::web::sse::SseManager;
-use crate::channels::web::state::{GatewayState, RateLimiter};
+use crate::channels::web::state::GatewayState;
 use crate::db::UserRole;
 
 pub(crate) struct TestLlmProvider {
@@ -51,11 +51,66 @@ impl crate::llm::LlmProvider for TestLlmProvider {
     }
 }
 
+/// Stands in for the email tool and records every send it is asked to make.
+pub(crate) struct RecordingEmailTool {
+    pub(crate) sent: Arc<std::sync::Mutex<Vec<serde_json::Value>>>,
+    /// When set, every send fails with this external-service error.
+    failure: Option<String>,
+}
+
+impl RecordingEmailTool {
+    pub(crate) fn new(sent: Arc<std::sync::Mutex<Vec<serde_json::Value>>>) -> Self {
+        Self {
+            sent,
+            failure: None,
+        }
+    }
+
+    /// A stub whose sends all fail with `reason`.
+    pub(crate) fn failing(reason: &str) -> Self {
+        Self {
+            sent: Arc::default(),
+            failure: Some(reason.to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl crate::tools::Tool for RecordingEmailTool {
+    fn name(&self) -> &str {
+        crate::legal::status_report::EMAIL_TOOL_NAME
+    }
+
+    fn description(&self) -> &str {
+        "records outgoing email"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({})
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        _ctx: &crate::context::JobContext,
+    ) -> Result<crate::tools::ToolOutput, crate::tools::ToolError> {
+        if let Some(reason) = &self.failure {
+            return Err(crate::tools::ToolError::ExternalService(reason.clone()));
+        }
+        self.sent.lock().expect("sent lock").push(params);
+        Ok(crate::tools::ToolOutput::text(
+            "sent",
+            std::time::Duration::ZERO,
+        ))
+    }
+}
+
 pub(crate) fn minimal_test_gateway_state(
     llm_provider: Option<Arc<dyn crate::llm::LlmProvider>>,
 ) -> Arc<GatewayState> {
     Arc::new(GatewayState {
         msg_tx: tokio::sync::RwLock::new(None),
+        intake: Arc::new(crate::channels::IntakeQueue::default()),
         sse: SseManager::new(),
         workspace: None,
         session_manager: None,
@@ -74,15 +129,21 @@ pub(crate) fn minimal_test_gateway_state(
         llm_provider,
         skill_registry: None,
         skill_catalog: None,
-        chat_rate_limiter: RateLimiter::new(30, 60),
+        rate_limiter: Arc::default(),
         registry_entries: Vec::new(),
         cost_guard: None,
+        review_queue: None,
+        routine_engine: None,
+        webhooks: None,
+        git_mirror: None,
+        events: crate::events::EventBus::new(),
         startup_time: std::time::Instant::now(),
         legal_config: Some(
             crate::config::LegalConfig::resolve(&crate::settings::Settings::default())
                 .expect("default legal config"),
         ),
         runtime_facts: crate::compliance::ComplianceRuntimeFacts::default(),
+        review_token_key: crate::channels::web::auth::derive_review_token_key("test-token"),
     })
 }
 
@@ -129,6 +190,7 @@ pub(crate) fn test_gateway_state_with_store_workspace_and_legal(
 ) -> Arc<GatewayState> {
     Arc::new(GatewayState {
         msg_tx: tokio::sync::RwLock::new(None),
+        intake: Arc::new(crate::channels::IntakeQueue::default()),
         sse: SseManager::new(),
         workspace: Some(workspace),
         session_manager: None,
@@ -147,12 +209,18 @@ pub(crate) fn test_gateway_state_with_store_workspace_and_legal(
         llm_provider: None,
         skill_registry: None,
         skill_catalog: None,
-        chat_rate_limiter: RateLimiter::new(30, 60),
+        rate_limiter: Arc::default(),
         registry_entries: Vec::new(),
         cost_guard: None,
+        review_queue: None,
+        routine_engine: None,
+        webhooks: None,
+        git_mirror: None,
+        events: crate::events::EventBus::new(),
         startup_time: std::time::Instant::now(),
         legal_config: Some(legal_config),
         runtime_facts: crate::compliance::ComplianceRuntimeFacts::default(),
+        review_token_key: crate::channels::web::auth::derive_review_token_key("test-token"),
     })
 }
 
@@ -175,6 +243,7 @@ pub(crate) fn test_gateway_state_with_store_workspace_and_chat(
 ) -> Arc<GatewayState> {
     Arc::new(GatewayState {
         msg_tx: tokio::sync::RwLock::new(None),
+        intake: Arc::new(crate::channels::IntakeQueue::default()),
         sse: SseManager::new(),
         workspace: Some(workspace),
         session_manager: Some(Arc::new(crate::agent::SessionManager::new())),
@@ -193,12 +262,18 @@ pub(crate) fn test_gateway_state_with_store_workspace_and_chat(
         llm_provider: None,
         skill_registry: None,
         skill_catalog: None,
-        chat_rate_limiter: RateLimiter::new(30, 60),
+        rate_limiter: Arc::default(),
         registry_entries: Vec::new(),
         cost_guard: None,
+        review_queue: None,
+        routine_engine: None,
+        webhooks: None,
+        git_mirror: None,
+        events: crate::events::EventBus::new(),
         startup_time: std::time::Instant::now(),
         legal_config: Some(test_legal_config()),
         runtime_facts: crate::compliance::ComplianceRuntimeFacts::default(),
+        review_token_key: crate::channels::web::auth::derive_review_token_key("test-token"),
     })
 }
 
@@ -212,6 +287,7 @@ pub(crate) fn test_gateway_state_for_user(
 ) -> Arc<GatewayState> {
     Arc::new(GatewayState {
         msg_tx: tokio::sync::RwLock::new(None),
+        intake: Arc::new(crate::channels::IntakeQueue::default()),
         sse: SseManager::new(),
         workspace: Some(workspace),
         session_manager: None,
@@ -230,12 +306,18 @@ pub(crate) fn test_gateway_state_for_user(
         llm_provider: None,
         skill_registry: None,
         skill_catalog: None,
-        chat_rate_limiter: RateLimiter::new(30, 60),
+        rate_limiter: Arc::default(),
         registry_entries: Vec::new(),
         cost_guard: None,
+        review_queue: None,
+        routine_engine: None,
+        webhooks: None,
+        git_mirror: None,
+        events: crate::events::EventBus::new(),
         startup_time: std::time::Instant::now(),
         legal_config: Some(test_legal_config()),
         runtime_facts: crate::compliance::ComplianceRuntimeFacts::default(),
+        review_token_key: crate::channels::web::auth::derive_review_token_key("test-token"),
     })
 }
 
@@ -275,3 +357,180 @@ pub(crate) async fn seed_valid_matter(workspace: &crate::workspace::Workspace, m
         .await
         .expect("seed notes document");
 }
+
+// ── Full-router harness ──────────────────────────────────────────────────────
+
+/// Bearer token and identity of a fixture user seeded by [`RouteHarness`].
+#[cfg(feature = "libsql")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct HarnessUser {
+    pub(crate) user_id: &'static str,
+    pub(crate) token: &'static str,
+}
+
+/// Gateway owner (`test-user`, admin). Owns every fixture matter.
+#[cfg(feature = "libsql")]
+pub(crate) const HARNESS_OWNER: HarnessUser = HarnessUser {
+    user_id: "test-user",
+    token: "harness-owner-token",
+};
+/// Attorney with the `collaborator` role on the fixture matter.
+#[cfg(feature = "libsql")]
+pub(crate) const HARNESS_COLLABORATOR: HarnessUser = HarnessUser {
+    user_id: "alice",
+    token: "harness-collaborator-token",
+};
+/// Staff user with the `viewer` role on the fixture matter.
+#[cfg(feature = "libsql")]
+pub(crate) const HARNESS_VIEWER: HarnessUser = HarnessUser {
+    user_id: "victor",
+    token: "harness-viewer-token",
+};
+/// Attorney with no membership on the fixture matter.
+#[cfg(feature = "libsql")]
+pub(crate) const HARNESS_OUTSIDER: HarnessUser = HarnessUser {
+    user_id: "mallory",
+    token: "harness-outsider-token",
+};
+
+/// Matter seeded by [`RouteHarness::new`].
+#[cfg(feature = "libsql")]
+pub(crate) const HARNESS_MATTER_ID: &str = "demo";
+
+/// The complete gateway router (auth middleware included) over a temporary
+/// libSQL database and workspace, seeded with one matter and a user per
+/// matter role. Requests are served in-process, so no port is bound.
+#[cfg(feature = "libsql")]
+pub(crate) struct RouteHarness {
+    pub(crate) db: Arc<dyn crate::db::Database>,
+    router: axum::Router,
+    _tmp: tempfile::TempDir,
+}
+
+#[cfg(feature = "libsql")]
+impl RouteHarness {
+    pub(crate) async fn new() -> Self {
+        use crate::channels::web::auth::{AuthState, compute_token_hash, derive_token_hmac_key};
+
+        let (db, tmp) = crate::testing::test_db().await;
+        let workspace = Arc::new(crate::workspace::Workspace::new_with_db(
+            HARNESS_OWNER.user_id,
+            Arc::clone(&db),
+        ));
+        seed_valid_matter(workspace.as_ref(), HARNESS_MATTER_ID).await;
+        let state = test_gateway_state_with_store_and_workspace(Arc::clone(&db), workspace);
+        crate::channels::web::server::ensure_matter_db_row_from_workspace(
+            state.as_ref(),
+            HARNESS_MATTER_ID,
+        )
+        .await
+        .expect("sync fixture matter row");
+
+        let hmac_key = derive_token_hmac_key(HARNESS_OWNER.token);
+        let fixtures = [
+            (HARNESS_OWNER, UserRole::Admin, None),
+            (
+                HARNESS_COLLABORATOR,
+                UserRole::Attorney,
+                Some(crate::db::MatterMemberRole::Collaborator),
+            ),
+            (
+                HARNESS_VIEWER,
+                UserRole::Staff,
+                Some(crate::db::MatterMemberRole::Viewer),
+            ),
+            (HARNESS_OUTSIDER, UserRole::Attorney, None),
+        ];
+        for (user, role, membership) in fixtures {
+            db.ensure_user_account(user.user_id, user.user_id, role)
+                .await
+                .expect("create fixture user");
+            db.upsert_user_token_hash(
+                user.user_id,
+                &compute_token_hash(user.token, Some(&hmac_key)),
+            )
+            .await
+            .expect("store fixture token");
+            if let Some(role) = membership {
+                db.upsert_matter_membership(&crate::db::UpsertMatterMembershipParams {
+                    matter_owner_user_id: HARNESS_OWNER.user_id.to_string(),
+                    matter_id: HARNESS_MATTER_ID.to_string(),
+                    member_user_id: user.user_id.to_string(),
+                    role,
+                })
+                .await
+                .expect("add fixture membership");
+            }
+        }
+
+        let auth_state = AuthState {
+            token: HARNESS_OWNER.token.to_string(),
+            fallback_principal: crate::channels::web::auth::AuthPrincipal::new(
+                HARNESS_OWNER.user_id,
+                UserRole::Admin,
+            ),
+            store: Some(Arc::clone(&db)),
+            hmac_key: Some(hmac_key),
+        };
+        let router = crate::channels::web::server::gateway_router(
+            state,
+            auth_state,
+            std::net::SocketAddr::from(([127, 0, 0, 1], 0)),
+        )
+        .expect("build gateway router");
+
+        Self {
+            db,
+            router,
+            _tmp: tmp,
+        }
+    }
+
+    /// Send one request through the router. `body` is sent as JSON.
+    pub(crate) async fn request(
+        &self,
+        method: axum::http::Method,
+        path: &str,
+        user: Option<HarnessUser>,
+        body: Option<serde_json::Value>,
+    ) -> (axum::http::StatusCode, serde_json::Value) {
+        use tower::ServiceExt;
+
+        let mut builder = axum::http::Request::builder().method(method).uri(path);
+        if let Some(user) = user {
+            builder = builder.header("authorization", format!("Bearer {}", user.token));
+        }
+        let request = match body {
+            Some(body) => builder
+                .header("content-type", "application/json")
+                .body(axum::body::Body::from(body.to_string())),
+            None => builder.body(axum::body::Body::empty()),
+        }
+        .expect("valid request");
+        let response = self
+            .router
+            .clone()
+            .oneshot(request)
+            .await
+            .expect("router is infallible");
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read response body");
+        let value = serde_json::from_slice(&bytes).unwrap_or_else(|_| {
+            serde_json::Value::String(String::from_utf8_lossy(&bytes).into_owned())
+        });
+        (status, value)
+    }
+
+    /// Status code `user` receives for `method path`.
+    pub(crate) async fn status(
+        &self,
+        method: axum::http::Method,
+        path: &str,
+        user: HarnessUser,
+        body: Option<serde_json::Value>,
+    ) -> axum::http::StatusCode {
+        self.request(method, path, Some(user), body).await.0
+    }
+}
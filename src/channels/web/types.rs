@@ -3,6 +3,9 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::agent::execution_window::ExecutionOverride;
+use crate::agent::routine::RoutineChain;
+
 // --- Chat ---
 
 #[derive(Debug, Deserialize)]
@@ -59,6 +62,70 @@ pub struct ToolCallInfo {
     pub has_error: bool,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct FileChatToMatterRequest {
+    pub matter_id: String,
+    /// Turn numbers from the history response; defaults to the latest
+    /// answered turn.
+    #[serde(default)]
+    pub turns: Vec<usize>,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Memo attribution; defaults to the requesting user.
+    #[serde(default)]
+    pub attorney: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FileChatToMatterResponse {
+    pub matter_id: String,
+    pub document: MatterDocumentInfo,
+}
+
+/// Branch a thread into a new one that shares its history up to `turn`.
+#[derive(Debug, Deserialize)]
+pub struct BranchThreadRequest {
+    /// Turn number from the history response; defaults to the latest turn.
+    #[serde(default)]
+    pub turn: Option<usize>,
+    /// Copy only the turns before `turn` and ask its question again in the
+    /// new thread, producing an alternative answer.
+    #[serde(default)]
+    pub regenerate: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BranchThreadResponse {
+    pub thread: ThreadInfo,
+    pub branched_from: Uuid,
+    pub turn: usize,
+    /// Turns copied into the new thread.
+    pub copied_turns: usize,
+    /// Message queued to regenerate the answer, when `regenerate` was set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<Uuid>,
+}
+
+/// Patch a thread's configuration. Omitted fields are left unchanged; an
+/// empty string or list clears the field.
+#[derive(Debug, Deserialize)]
+pub struct UpdateThreadConfigRequest {
+    #[serde(default)]
+    pub system_prompt_additions: Option<Option<String>>,
+    /// `formal`, `plain`, `concise`, or `detailed`.
+    #[serde(default)]
+    pub tone: Option<Option<String>>,
+    /// Tool names the thread may use; cleared, every tool is enabled.
+    #[serde(default)]
+    pub enabled_tools: Option<Option<Vec<String>>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ThreadConfigResponse {
+    pub thread_id: Uuid,
+    pub config: crate::agent::thread_config::ThreadConfig,
+}
+
 #[derive(Debug, Serialize)]
 pub struct HistoryResponse {
     pub thread_id: Uuid,
@@ -84,11 +151,25 @@ pub struct ApprovalRequest {
 
 // --- SSE Event Types ---
 
+/// A resolved `[[chunk:<id>]]` anchor in an assistant response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkCitation {
+    pub chunk_id: String,
+    pub path: String,
+    pub chunk_index: i32,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "type")]
 pub enum SseEvent {
     #[serde(rename = "response")]
-    Response { content: String, thread_id: String },
+    Response {
+        content: String,
+        thread_id: String,
+        /// Chunks cited via `[[chunk:<id>]]` anchors in `content`.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        citations: Vec<ChunkCitation>,
+    },
     #[serde(rename = "thinking")]
     Thinking {
         message: String,
@@ -166,6 +247,16 @@ pub enum SseEvent {
     },
     #[serde(rename = "heartbeat")]
     Heartbeat,
+    /// Someone is (or stopped) composing a message in a thread.
+    #[serde(rename = "typing")]
+    Typing {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thread_id: Option<String>,
+        active: bool,
+        /// WebSocket connection that sent it, so it is not echoed back.
+        #[serde(skip)]
+        origin: Option<String>,
+    },
 
     // Sandbox job streaming events (worker + Claude Code bridge)
     #[serde(rename = "job_message")]
@@ -195,6 +286,63 @@ pub enum SseEvent {
         #[serde(skip_serializing_if = "Option::is_none")]
         session_id: Option<String>,
     },
+
+    // Collaborative matter notes
+    /// A note on a matter was created, updated, or deleted. Events reach
+    /// every connection, so they carry no note text; clients refetch the
+    /// notes endpoint, which checks matter access.
+    #[serde(rename = "matter_note")]
+    MatterNote {
+        matter_id: String,
+        /// "created", "updated", or "deleted"
+        action: String,
+        note_id: String,
+        actor: String,
+    },
+    /// A matter note @mentioned a matter participant. The excerpt goes to
+    /// the mentioned user's inbox only.
+    #[serde(rename = "matter_note_mention")]
+    MatterNoteMention {
+        matter_id: String,
+        note_id: String,
+        mentioned_user_id: String,
+        author: String,
+    },
+    /// A domain event from the event bus (e.g. `matter.created`).
+    #[serde(rename = "domain_event")]
+    Domain {
+        event: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        matter_id: Option<String>,
+        data: serde_json::Value,
+    },
+    /// A new entry in the notification inbox.
+    #[serde(rename = "notification")]
+    Notification(NotificationInfo),
+}
+
+impl SseEvent {
+    /// Matter this event is scoped to, for per-matter WebSocket subscriptions.
+    pub fn matter_id(&self) -> Option<&str> {
+        match self {
+            SseEvent::MatterNote { matter_id, .. } => Some(matter_id),
+            SseEvent::Domain { matter_id, .. } => matter_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Sandbox job this event streams output for, for per-job WebSocket
+    /// subscriptions. `job_started` is announced to everyone.
+    pub fn job_id(&self) -> Option<&str> {
+        match self {
+            SseEvent::JobMessage { job_id, .. }
+            | SseEvent::JobToolUse { job_id, .. }
+            | SseEvent::JobToolResult { job_id, .. }
+            | SseEvent::JobStatus { job_id, .. }
+            | SseEvent::JobResult { job_id, .. } => Some(job_id),
+            _ => None,
+        }
+    }
 }
 
 // --- Memory ---
@@ -229,6 +377,9 @@ pub struct MemoryReadResponse {
     pub path: String,
     pub content: String,
     pub updated_at: Option<String>,
+    /// Send back as `If-Match` on write to reject the write if the document
+    /// changed in the meantime.
+    pub etag: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -241,12 +392,201 @@ pub struct MemoryWriteRequest {
 pub struct MemoryWriteResponse {
     pub path: String,
     pub status: &'static str,
+    pub etag: String,
+    /// Soft storage quota warning for the matter that owns `path`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quota_warning: Option<String>,
+}
+
+/// 409 body for a write whose `If-Match` no longer matches.
+#[derive(Debug, Serialize)]
+pub struct MemoryWriteConflictResponse {
+    pub error: String,
+    pub path: String,
+    /// `None` when the document no longer exists.
+    pub current_etag: Option<String>,
+    pub current_content: Option<String>,
+    /// Line diff from the version the writer read to the current content,
+    /// when that version is still in the document history.
+    pub changes_since_read: Option<String>,
+    /// Line diff from the current content to the rejected write.
+    pub proposed_changes: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MemoryDeleteRequest {
+    pub path: String,
+}
+
+/// A deleted document waiting in the workspace trash.
+#[derive(Debug, Serialize)]
+pub struct TrashEntryInfo {
+    pub id: String,
+    pub path: String,
+    pub deleted_by: String,
+    pub deleted_at: String,
+    /// When the document was last written before deletion.
+    pub document_updated_at: String,
+    /// Stored size (ciphertext for encrypted matter files).
+    pub size_bytes: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemoryTrashResponse {
+    pub entries: Vec<TrashEntryInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemoryRestoreResponse {
+    pub id: String,
+    pub path: String,
+    pub status: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateShareLinkRequest {
+    /// Workspace document, served as markdown.
+    pub path: String,
+    /// Link lifetime; defaults to 72 hours, at most 30 days.
+    #[serde(default)]
+    pub expires_in_hours: Option<i64>,
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Who the link is for, e.g. "Smith & Co (co-counsel)".
+    #[serde(default)]
+    pub recipient: Option<String>,
+    /// Stamp downloaded copies with a confidentiality watermark (default on).
+    #[serde(default)]
+    pub watermark: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShareLinkInfo {
+    pub id: String,
+    pub path: String,
+    pub matter_id: Option<String>,
+    pub recipient: Option<String>,
+    pub password_protected: bool,
+    pub watermark: bool,
+    pub created_by: String,
+    pub expires_at: String,
+    pub revoked_at: Option<String>,
+    /// "active" | "expired" | "revoked"
+    pub status: &'static str,
+    pub download_count: i64,
+    pub last_downloaded_at: Option<String>,
+    /// Wrong passwords since the last successful download.
+    pub failed_password_attempts: i64,
+    pub created_at: String,
+}
+
+/// Returned once at creation; the token is not stored and cannot be
+/// recovered later.
+#[derive(Debug, Serialize)]
+pub struct CreateShareLinkResponse {
+    #[serde(flatten)]
+    pub link: ShareLinkInfo,
+    pub token: String,
+    /// Relative download URL, to be prefixed with the gateway's public origin.
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ShareLinksQuery {
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShareLinksResponse {
+    pub links: Vec<ShareLinkInfo>,
+}
+
+/// Body for downloading a password-protected share link.
+#[derive(Debug, Deserialize)]
+pub struct ShareDownloadRequest {
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MemoryHistoryQuery {
+    pub path: String,
+    /// Return only this revision, including its full content.
+    pub revision: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DocumentRevisionInfo {
+    pub revision: i64,
+    pub content_hash: String,
+    pub size_bytes: usize,
+    pub created_at: String,
+    /// Line diff against the previous revision (` `, `-`, `+` prefixed lines).
+    pub diff: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemoryHistoryResponse {
+    pub path: String,
+    pub revisions: Vec<DocumentRevisionInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MemoryRestoreRevisionRequest {
+    pub path: String,
+    pub revision: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemoryRestoreRevisionResponse {
+    pub path: String,
+    pub restored_revision: i64,
+    pub status: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemoryGitStatusResponse {
+    pub repo_path: String,
+    pub branch: String,
+    pub remote: Option<String>,
+    /// Branch head, absent before the first sync.
+    pub head: Option<String>,
+    /// Paths changed through the API that the next sync attributes to users.
+    pub pending_user_changes: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemoryGitSyncResponse {
+    pub commits: Vec<crate::workspace::git_mirror::MirrorCommit>,
+    pub head: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MemoryGitImportRequest {
+    /// Commit, tag, or branch to import; defaults to the mirror branch.
+    #[serde(default)]
+    pub rev: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemoryGitImportResponse {
+    pub rev: String,
+    pub imported: usize,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct MemorySearchRequest {
     pub query: String,
     pub limit: Option<usize>,
+    /// Restrict hits to this matter's workspace directory.
+    #[serde(default)]
+    pub matter_id: Option<String>,
+    /// Only documents carrying every `key[=value]` tag.
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -257,10 +597,80 @@ pub struct MemorySearchResponse {
 #[derive(Debug, Serialize)]
 pub struct SearchHit {
     pub path: String,
+    pub chunk_id: String,
     pub content: String,
     pub score: f64,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct DocumentTagsQuery {
+    pub path: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteDocumentTagQuery {
+    pub path: String,
+    pub key: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DocumentTagInput {
+    pub key: String,
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetDocumentTagsRequest {
+    pub path: String,
+    pub tags: Vec<DocumentTagInput>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DocumentTagInfo {
+    pub key: String,
+    pub value: Option<String>,
+    pub created_by: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DocumentTagsResponse {
+    pub path: String,
+    pub tags: Vec<DocumentTagInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DocumentTagFacetsQuery {
+    /// Only count documents under this path.
+    pub prefix: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DocumentTagFacet {
+    pub key: String,
+    pub value: Option<String>,
+    pub documents: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DocumentTagFacetsResponse {
+    pub facets: Vec<DocumentTagFacet>,
+}
+
+/// Response for `GET /api/memory/chunks/{chunk_id}`.
+#[derive(Debug, Serialize)]
+pub struct MemoryChunkResponse {
+    pub chunk_id: String,
+    pub document_id: String,
+    pub path: String,
+    pub chunk_index: i32,
+    pub content: String,
+    /// The cited passage exactly as it appears in the current document,
+    /// for highlighting. `None` if the document changed since indexing.
+    pub passage: Option<String>,
+}
+
 // --- Matters ---
 
 /// Single matter returned by `GET /api/matters`.
@@ -341,80 +751,330 @@ pub struct UpdateClientRequest {
 }
 
 #[derive(Debug, Serialize)]
-pub struct MatterTaskInfo {
+pub struct ClientCommunicationPreferencesInfo {
+    pub allowed_channels: Vec<String>,
+    pub quiet_hours: Vec<crate::agent::execution_window::ExecutionWindow>,
+    pub timezone: Option<String>,
+    pub language: Option<String>,
+    pub disclaimers: Vec<String>,
+    pub updated_by: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClientCommunicationConsentInfo {
     pub id: String,
-    pub title: String,
-    pub description: Option<String>,
+    pub channel: String,
     pub status: String,
-    pub assignee: Option<String>,
-    pub due_at: Option<String>,
-    pub blocked_by: Vec<String>,
+    pub source: String,
+    pub consented_at: String,
+    pub recorded_by: String,
     pub created_at: String,
-    pub updated_at: String,
 }
 
 #[derive(Debug, Serialize)]
-pub struct MatterTasksListResponse {
-    pub tasks: Vec<MatterTaskInfo>,
+pub struct ClientCommunicationsResponse {
+    pub client_id: String,
+    /// `None` until preferences are saved.
+    pub preferences: Option<ClientCommunicationPreferencesInfo>,
+    /// Consent decision in force per channel.
+    pub consents: Vec<ClientCommunicationConsentInfo>,
+    /// Every consent decision, newest first.
+    pub history: Vec<ClientCommunicationConsentInfo>,
 }
 
 #[derive(Debug, Deserialize)]
-pub struct CreateMatterTaskRequest {
-    pub title: String,
+pub struct UpdateClientCommunicationPreferencesRequest {
     #[serde(default)]
-    pub description: Option<String>,
+    pub allowed_channels: Vec<String>,
+    /// Weekly windows (`{"days": ["sat"], "start": "21:00", "end": "08:00"}`)
+    /// in which the firm does not start contact.
     #[serde(default)]
-    pub status: Option<String>,
+    pub quiet_hours: Vec<crate::agent::execution_window::ExecutionWindow>,
     #[serde(default)]
-    pub assignee: Option<String>,
+    pub timezone: Option<String>,
     #[serde(default)]
-    pub due_at: Option<String>,
+    pub language: Option<String>,
     #[serde(default)]
-    pub blocked_by: Vec<String>,
+    pub disclaimers: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
-pub struct UpdateMatterTaskRequest {
-    #[serde(default)]
-    pub title: Option<String>,
-    #[serde(default)]
-    pub description: Option<Option<String>>,
-    #[serde(default)]
-    pub status: Option<String>,
-    #[serde(default)]
-    pub assignee: Option<Option<String>>,
-    #[serde(default)]
-    pub due_at: Option<Option<String>>,
+pub struct RecordClientConsentRequest {
+    pub channel: String,
+    /// `granted` or `revoked`.
+    pub status: String,
+    /// Where the decision came from, e.g. `engagement letter`.
+    pub source: String,
+    /// When the client decided (`YYYY-MM-DD` or RFC 3339); defaults to now.
     #[serde(default)]
-    pub blocked_by: Option<Vec<String>>,
+    pub consented_at: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
-pub struct MatterNoteInfo {
+pub struct MatterTaskInfo {
     pub id: String,
-    pub author: String,
-    pub body: String,
-    pub pinned: bool,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub assignee: Option<String>,
+    pub due_at: Option<String>,
+    pub blocked_by: Vec<String>,
+    pub position: i64,
+    pub parent_task_id: Option<String>,
+    pub checklist: Vec<crate::db::MatterTaskChecklistItem>,
     pub created_at: String,
     pub updated_at: String,
 }
 
 #[derive(Debug, Serialize)]
-pub struct MatterNotesListResponse {
-    pub notes: Vec<MatterNoteInfo>,
+pub struct MatterTasksListResponse {
+    pub tasks: Vec<MatterTaskInfo>,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct CreateMatterNoteRequest {
-    pub author: String,
-    pub body: String,
+/// Query for `GET /api/matters/{id}/tasks/board`. `swimlane` is `status`
+/// (default) or `assignee`.
+#[derive(Debug, Default, Deserialize)]
+pub struct MatterTaskBoardQuery {
     #[serde(default)]
-    pub pinned: bool,
+    pub swimlane: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct UpdateMatterNoteRequest {
-    #[serde(default)]
+#[derive(Debug, Serialize)]
+pub struct MatterTaskBoardCard {
+    #[serde(flatten)]
+    pub task: MatterTaskInfo,
+    /// True when the task is marked blocked or waits on an open dependency.
+    pub is_blocked: bool,
+    /// `blocked_by` tasks on the matter that are not yet done or cancelled.
+    pub open_blockers: Vec<String>,
+    /// Tasks that list this one in their `blocked_by`.
+    pub blocks: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterTaskBoardLane {
+    /// Status or assignee for the lane; `null` is the unassigned lane.
+    pub key: Option<String>,
+    pub tasks: Vec<MatterTaskBoardCard>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterTaskBoardResponse {
+    pub matter_id: String,
+    pub swimlane: String,
+    pub lanes: Vec<MatterTaskBoardLane>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MatterTaskMoveRequest {
+    pub task_id: String,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub assignee: Option<Option<String>>,
+    pub position: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MoveMatterTasksRequest {
+    pub moves: Vec<MatterTaskMoveRequest>,
+    /// Lane grouping for the returned board.
+    #[serde(default)]
+    pub swimlane: Option<String>,
+}
+
+/// Renumber the listed tasks 0..n in the given order without changing lanes.
+#[derive(Debug, Deserialize)]
+pub struct ReorderMatterTasksRequest {
+    pub task_ids: Vec<String>,
+    #[serde(default)]
+    pub swimlane: Option<String>,
+}
+
+/// Filters for `GET /api/matters/{id}/chronology`. Dates are `YYYY-MM-DD`
+/// and inclusive.
+#[derive(Debug, Default, Deserialize)]
+pub struct MatterChronologyQuery {
+    #[serde(default)]
+    pub from: Option<String>,
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Only facts extracted from this workspace path.
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub min_confidence: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterFactInfo {
+    pub id: String,
+    pub date: String,
+    pub description: String,
+    pub source_path: String,
+    pub source_locator: Option<String>,
+    pub source_excerpt: Option<String>,
+    pub confidence: f64,
+    pub extractor: String,
+    pub created_by: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterChronologyResponse {
+    pub matter_id: String,
+    pub facts: Vec<MatterFactInfo>,
+}
+
+/// Filters for `GET /api/matters/{id}/timeline` and its export. Dates are
+/// `YYYY-MM-DD` and inclusive; `types` is a comma-separated subset of
+/// `fact,docket,deadline,communication,document`.
+#[derive(Debug, Default, Deserialize)]
+pub struct MatterTimelineQuery {
+    #[serde(default)]
+    pub from: Option<String>,
+    #[serde(default)]
+    pub to: Option<String>,
+    #[serde(default)]
+    pub types: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterTimelineResponse {
+    pub matter_id: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub types: Vec<&'static str>,
+    pub events: Vec<crate::legal::timeline::TimelineEvent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterTimelineExportResponse {
+    pub matter_id: String,
+    pub path: String,
+    pub generated_at: String,
+    pub event_count: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MedicalChronologyResponse {
+    pub matter_id: String,
+    pub records_scanned: usize,
+    /// Records under `medical/records/` with no dated visit; these need a
+    /// manual read.
+    pub records_without_visits: Vec<String>,
+    pub providers: Vec<crate::legal::medical_records::ProviderSummary>,
+    pub visits: Vec<crate::legal::medical_records::MedicalVisit>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MedicalChronologyExportResponse {
+    pub matter_id: String,
+    pub path: String,
+    pub data_path: String,
+    pub generated_at: String,
+    pub visit_count: usize,
+    pub provider_count: usize,
+}
+
+/// Compare two matter documents, e.g. the last draft (`base_path`) and the
+/// text extracted from the executed copy (`compared_path`).
+#[derive(Debug, Deserialize)]
+pub struct CompareDocumentsRequest {
+    pub base_path: String,
+    pub compared_path: String,
+    /// File a change memo under the matter's `memos/` folder.
+    #[serde(default)]
+    pub write_memo: bool,
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompareDocumentsResponse {
+    pub matter_id: String,
+    pub base_path: String,
+    pub compared_path: String,
+    pub comparison: crate::legal::compare::DocumentComparison,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo_document_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateMatterTaskRequest {
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub due_at: Option<String>,
+    #[serde(default)]
+    pub blocked_by: Vec<String>,
+    #[serde(default)]
+    pub parent_task_id: Option<String>,
+    #[serde(default)]
+    pub checklist: Vec<crate::db::MatterTaskChecklistItem>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateMatterTaskRequest {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<Option<String>>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub assignee: Option<Option<String>>,
+    #[serde(default)]
+    pub due_at: Option<Option<String>>,
+    #[serde(default)]
+    pub blocked_by: Option<Vec<String>>,
+    #[serde(default)]
+    pub parent_task_id: Option<Option<String>>,
+    /// Replaces the whole checklist; item order is kept.
+    #[serde(default)]
+    pub checklist: Option<Vec<crate::db::MatterTaskChecklistItem>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MatterNoteInfo {
+    pub id: String,
+    pub author: String,
+    pub body: String,
+    pub pinned: bool,
+    /// Root note of the thread this note replies to.
+    pub parent_id: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterNotesListResponse {
+    pub notes: Vec<MatterNoteInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateMatterNoteRequest {
+    pub author: String,
+    pub body: String,
+    #[serde(default)]
+    pub pinned: bool,
+    /// Reply to this note; replies to a reply join its root's thread.
+    #[serde(default)]
+    pub parent_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateMatterNoteRequest {
+    #[serde(default)]
     pub author: Option<String>,
     #[serde(default)]
     pub body: Option<String>,
@@ -912,6 +1572,10 @@ pub struct CreateMatterRequest {
     pub conflict_decision: Option<crate::db::ConflictDecision>,
     #[serde(default)]
     pub conflict_note: Option<String>,
+    /// Custom field values, validated against the firm's schemas for the
+    /// practice area.
+    #[serde(default)]
+    pub custom_fields: Option<serde_json::Value>,
 }
 
 /// Response body for `POST /api/matters`.
@@ -943,6 +1607,76 @@ pub struct UpdateMatterRequest {
     pub custom_fields: Option<serde_json::Value>,
 }
 
+/// Request body for `POST /api/matters/{id}/stage`.
+#[derive(Debug, Deserialize)]
+pub struct MatterStageTransitionRequest {
+    pub stage: String,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// A stage the matter can move to, with any unmet entry prerequisites.
+#[derive(Debug, Serialize)]
+pub struct MatterStageOption {
+    pub id: String,
+    pub label: String,
+    /// Missing artifacts and incomplete checklists; empty when ready.
+    pub missing: Vec<String>,
+}
+
+/// Response body for `GET /api/matters/{id}/stage`.
+#[derive(Debug, Serialize)]
+pub struct MatterStageResponse {
+    pub workflow_id: String,
+    pub workflow_label: String,
+    pub stage: Option<String>,
+    pub stage_label: Option<String>,
+    pub next: Vec<MatterStageOption>,
+}
+
+/// Response body for `POST /api/matters/{id}/stage`.
+#[derive(Debug, Serialize)]
+pub struct MatterStageTransitionResponse {
+    pub matter: MatterInfo,
+    pub workflow_id: String,
+    pub from: Option<String>,
+    pub to: String,
+    pub created_tasks: Vec<MatterTaskInfo>,
+}
+
+/// Response body for `GET /api/matters/{id}/close`.
+#[derive(Debug, Serialize)]
+pub struct MatterCloseoutStatusResponse {
+    pub matter_id: String,
+    pub status: String,
+    /// True when every check passes and the matter is not already closed.
+    pub ready: bool,
+    pub checks: Vec<crate::legal::closeout::CloseoutCheck>,
+    pub retention_years: u32,
+}
+
+/// Request body for `POST /api/matters/{id}/close`.
+#[derive(Debug, Default, Deserialize)]
+pub struct CloseMatterRequest {
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Overrides the period named by the matter's retention policy.
+    #[serde(default)]
+    pub retention_years: Option<u32>,
+}
+
+/// Response body for `POST /api/matters/{id}/close`.
+#[derive(Debug, Serialize)]
+pub struct CloseMatterResponse {
+    pub matter: MatterInfo,
+    pub checks: Vec<crate::legal::closeout::CloseoutCheck>,
+    pub closing_letter_path: String,
+    pub closeout_report_path: String,
+    pub closed_at: String,
+    pub retain_until: String,
+    pub retention_deadline_id: Option<String>,
+}
+
 /// Request body for `POST /api/matters/conflicts/check`.
 #[derive(Debug, Deserialize)]
 pub struct MatterConflictCheckRequest {
@@ -1008,6 +1742,54 @@ pub struct UpsertMatterPartyRequest {
     pub closed_at: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct PartyCandidateInfo {
+    pub id: String,
+    pub matter_id: String,
+    pub name: String,
+    pub entity_type: String,
+    pub source_path: String,
+    pub snippet: Option<String>,
+    pub confidence: f64,
+    pub extractor: String,
+    pub status: String,
+    pub party_id: Option<String>,
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<String>,
+    pub created_at: String,
+    /// Existing conflict-graph hits for this name on other matters.
+    pub conflict_hits: Vec<crate::db::ConflictHit>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterPartyCandidatesResponse {
+    pub matter_id: String,
+    pub candidates: Vec<PartyCandidateInfo>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PartyCandidatesQuery {
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AcceptPartyCandidateRequest {
+    pub role: String,
+    /// Overrides the extracted name (e.g. to use the full legal name).
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartyCandidateReviewResponse {
+    pub candidate: PartyCandidateInfo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub party: Option<MatterPartyInfo>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct PartyRelationshipInfo {
     pub id: String,
@@ -1038,6 +1820,38 @@ pub struct CreatePartyRelationshipRequest {
     pub kind: String,
 }
 
+#[derive(Debug, Default, Deserialize)]
+pub struct MatterEntitiesQuery {
+    /// Restrict the entity list to `person`, `organization`, `date`, or `amount`.
+    #[serde(default)]
+    pub kind: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterEntityMention {
+    pub source_path: String,
+    pub snippet: Option<String>,
+}
+
+/// One entity across a matter's documents, merged by normalized value.
+#[derive(Debug, Serialize)]
+pub struct MatterEntityInfo {
+    pub kind: String,
+    pub value: String,
+    pub normalized_value: String,
+    /// Highest confidence among the mentions.
+    pub confidence: f64,
+    pub mentions: Vec<MatterEntityMention>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterEntitiesResponse {
+    pub matter_id: String,
+    pub entities: Vec<MatterEntityInfo>,
+    pub nodes: Vec<crate::legal::entities::PartyGraphNode>,
+    pub edges: Vec<crate::legal::entities::PartyGraphEdge>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct MatterConflictReportResponse {
     pub matter_id: String,
@@ -1083,6 +1897,39 @@ pub struct MatterIntakeConflictCheckResponse {
     pub checked_parties: Vec<String>,
 }
 
+/// Request body for `POST /api/legal/conflicts/bulk-check`. Names come from
+/// `names`, a `csv` upload, or both.
+#[derive(Debug, Deserialize)]
+pub struct BulkConflictCheckRequest {
+    #[serde(default)]
+    pub names: Vec<String>,
+    #[serde(default)]
+    pub csv: Option<String>,
+    /// What the list is, e.g. "Lateral hire: J. Smith prior clients".
+    #[serde(default)]
+    pub label: Option<String>,
+    /// "json" (default) or "csv" for a downloadable report.
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// Conflict hits for one name in a bulk check.
+#[derive(Debug, Serialize)]
+pub struct BulkConflictCheckResult {
+    pub name: String,
+    pub matched: bool,
+    pub hits: Vec<crate::db::ConflictHit>,
+}
+
+/// Response body for `POST /api/legal/conflicts/bulk-check`.
+#[derive(Debug, Serialize)]
+pub struct BulkConflictCheckResponse {
+    pub label: Option<String>,
+    pub checked_count: usize,
+    pub matched_count: usize,
+    pub results: Vec<BulkConflictCheckResult>,
+}
+
 /// Response body for `POST /api/matters/conflicts/reindex`.
 #[derive(Debug, Serialize)]
 pub struct MatterConflictGraphReindexResponse {
@@ -1090,24 +1937,118 @@ pub struct MatterConflictGraphReindexResponse {
     pub report: crate::legal::matter::ConflictGraphReindexReport,
 }
 
-// --- Legal audit ---
+// --- Party watchlist ---
 
 #[derive(Debug, Serialize)]
-pub struct LegalAuditEventInfo {
+pub struct WatchlistEntryInfo {
     pub id: String,
-    pub ts: String,
-    pub event_type: String,
-    pub actor: String,
-    pub matter_id: Option<String>,
-    pub severity: String,
-    pub details: serde_json::Value,
+    pub name: String,
+    /// "adverse_party", "sanctioned", or "other".
+    pub category: &'static str,
+    pub reason: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
 }
 
 #[derive(Debug, Serialize)]
-pub struct LegalAuditListResponse {
-    pub events: Vec<LegalAuditEventInfo>,
-    pub total: usize,
-    pub next_offset: Option<usize>,
+pub struct WatchlistListResponse {
+    pub entries: Vec<WatchlistEntryInfo>,
+}
+
+/// Request body for `POST /api/legal/watchlist`.
+#[derive(Debug, Deserialize)]
+pub struct CreateWatchlistEntryRequest {
+    pub name: String,
+    /// Defaults to "adverse_party".
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Request body for `POST /api/legal/watchlist/screen`.
+#[derive(Debug, Deserialize)]
+pub struct WatchlistScreenRequest {
+    #[serde(default)]
+    pub names: Vec<String>,
+    /// Free text (e.g. a pasted engagement letter) scanned for watched names.
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WatchlistScreenResponse {
+    pub matched: bool,
+    pub matches: Vec<crate::legal::watchlist::WatchlistMatch>,
+}
+
+// --- Legal audit ---
+
+#[derive(Debug, Serialize)]
+pub struct LegalAuditEventInfo {
+    pub id: String,
+    pub ts: String,
+    pub event_type: String,
+    pub actor: String,
+    pub matter_id: Option<String>,
+    pub severity: String,
+    pub details: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LegalAuditListResponse {
+    pub events: Vec<LegalAuditEventInfo>,
+    pub total: usize,
+    pub next_offset: Option<usize>,
+}
+
+// --- Legal costs ---
+
+#[derive(Debug, Default, Deserialize)]
+pub struct LegalCostsQuery {
+    #[serde(default)]
+    pub group_by: Option<String>,
+    #[serde(default)]
+    pub since: Option<String>,
+    #[serde(default)]
+    pub until: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LegalCostGroupInfo {
+    /// Matter ID, client ID, source, or day; null for unattributed spend.
+    pub key: Option<String>,
+    pub cost_usd: String,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub entries: usize,
+    /// Spend cap fields are only reported when grouping by matter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monthly_cap_cents: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub month_to_date_usd: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cap_exceeded: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LegalCostsResponse {
+    pub group_by: String,
+    pub total_usd: String,
+    pub groups: Vec<LegalCostGroupInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MatterSpendCapRequest {
+    /// Monthly cap in cents; null clears it.
+    pub monthly_cap_cents: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterSpendCapResponse {
+    pub matter_id: String,
+    pub monthly_cap_cents: Option<i64>,
+    pub month_to_date_usd: String,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -1201,6 +2142,20 @@ pub struct BackupCreateResponse {
     pub manifest: serde_json::Value,
 }
 
+#[derive(Debug, Serialize)]
+pub struct BackupListEntry {
+    pub id: String,
+    pub created_at: String,
+    pub size_bytes: u64,
+    pub user_id: String,
+    pub app_version: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackupListResponse {
+    pub backups: Vec<BackupListEntry>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct BackupVerifyRequest {
     pub backup_id: String,
@@ -1299,6 +2254,182 @@ pub struct MatterRetrievalExportResponse {
     pub warning: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct TemplateVariablesResponse {
+    pub template_id: String,
+    pub matter_id: Option<String>,
+    pub name: String,
+    pub variables: Vec<crate::legal::docgen::TemplateVariable>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTemplateVariablesRequest {
+    /// Array of `{name, type, required, description, choices}` entries.
+    pub variables: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SharedTemplateInfo {
+    pub id: String,
+    pub name: String,
+    pub practice_area: Option<String>,
+    pub jurisdiction: Option<String>,
+    pub description: Option<String>,
+    pub promoted_from: Option<String>,
+    pub variables: serde_json::Value,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SharedTemplatesResponse {
+    pub templates: Vec<SharedTemplateInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SharedTemplateDetailResponse {
+    #[serde(flatten)]
+    pub template: SharedTemplateInfo,
+    pub body: String,
+    /// Latest recorded version number; 0 before the first snapshot.
+    pub version: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SharedTemplatesQuery {
+    #[serde(default)]
+    pub practice_area: Option<String>,
+    #[serde(default)]
+    pub jurisdiction: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSharedTemplateRequest {
+    pub name: String,
+    pub body: String,
+    #[serde(default)]
+    pub variables: Option<serde_json::Value>,
+    #[serde(default)]
+    pub practice_area: Option<String>,
+    #[serde(default)]
+    pub jurisdiction: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Omitted fields are left unchanged; category fields set to `""` are
+/// cleared.
+#[derive(Debug, Deserialize)]
+pub struct UpdateSharedTemplateRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub variables: Option<serde_json::Value>,
+    #[serde(default)]
+    pub practice_area: Option<String>,
+    #[serde(default)]
+    pub jurisdiction: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PromoteTemplateRequest {
+    /// Shared name; defaults to the matter template's name.
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub practice_area: Option<String>,
+    #[serde(default)]
+    pub jurisdiction: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateVersionInfo {
+    pub version: i32,
+    pub name: String,
+    pub body: String,
+    pub variables: serde_json::Value,
+    pub created_by: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateVersionsResponse {
+    pub template_id: String,
+    pub versions: Vec<TemplateVersionInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TemplateAnalyticsQuery {
+    /// RFC 3339 timestamp or `YYYY-MM-DD`; all time when omitted.
+    #[serde(default)]
+    pub since: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateUsageInfo {
+    pub template_id: String,
+    pub name: String,
+    pub matter_id: Option<String>,
+    pub practice_area: Option<String>,
+    pub jurisdiction: Option<String>,
+    pub uses: i64,
+    pub matters: i64,
+    pub last_used_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateAnalyticsResponse {
+    pub since: Option<String>,
+    pub total_uses: i64,
+    pub templates: Vec<TemplateUsageInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DocumentCaptionRequest {
+    pub matter_id: String,
+    /// Document title printed under the caption.
+    pub title: String,
+    /// Overrides the matter's jurisdiction for profile selection.
+    #[serde(default)]
+    pub jurisdiction: Option<String>,
+    /// Court heading; one line per `\n`.
+    #[serde(default)]
+    pub court: Option<String>,
+    #[serde(default)]
+    pub case_number: Option<String>,
+    /// `plaintiff` or `defendant`.
+    #[serde(default)]
+    pub client_position: Option<String>,
+    #[serde(default)]
+    pub plaintiffs: Option<Vec<String>>,
+    #[serde(default)]
+    pub defendants: Option<Vec<String>>,
+    #[serde(default)]
+    pub attorney: Option<crate::legal::caption::CaptionSigner>,
+    #[serde(default)]
+    pub served: Vec<crate::legal::caption::ServiceEntry>,
+    /// `YYYY-MM-DD`; defaults to today.
+    #[serde(default)]
+    pub date: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DocumentCaptionResponse {
+    pub style: &'static str,
+    pub court_heading: Vec<String>,
+    pub case_number: Option<String>,
+    pub plaintiffs: Vec<String>,
+    pub defendants: Vec<String>,
+    pub caption: String,
+    pub signature_block: Option<String>,
+    pub certificate_of_service: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct GenerateDocumentRequest {
     pub template_id: String,
@@ -1311,6 +2442,10 @@ pub struct GenerateDocumentRequest {
     pub category: Option<String>,
     #[serde(default)]
     pub label: Option<String>,
+    /// Locale code (`es`, `en-GB`). Defaults to the client's preferred
+    /// language, then US English.
+    #[serde(default)]
+    pub locale: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1323,6 +2458,10 @@ pub struct GenerateDocumentResponse {
     pub readiness_state: String,
     pub version_number: i32,
     pub label: String,
+    /// Locale the document was rendered in.
+    pub locale: String,
+    /// Template actually rendered: the locale variant when one exists.
+    pub template_name: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1397,6 +2536,105 @@ pub struct MarkDocumentReadyResponse {
     pub document: MatterDocumentInfo,
 }
 
+#[derive(Debug, Serialize)]
+pub struct DocumentTranslationInfo {
+    pub id: String,
+    pub matter_id: String,
+    pub source_path: String,
+    pub source_language: Option<String>,
+    pub target_language: String,
+    pub translation_path: String,
+    pub provider: String,
+    pub status: String,
+    pub translator: Option<String>,
+    pub certified_path: Option<String>,
+    pub certified_at: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DocumentTranslationsResponse {
+    pub translations: Vec<DocumentTranslationInfo>,
+}
+
+/// Side-by-side view of a source document and its translations.
+#[derive(Debug, Serialize)]
+pub struct DocumentTranslationDetailResponse {
+    pub translation: DocumentTranslationInfo,
+    pub source_content: Option<String>,
+    pub machine_translation_content: Option<String>,
+    pub certified_content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RequestTranslationCertificationRequest {
+    #[serde(default)]
+    pub translator: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CertifyTranslationRequest {
+    pub certified_path: String,
+    pub translator: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DocumentTranslationResponse {
+    pub translation: DocumentTranslationInfo,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClientStatusReportInfo {
+    pub id: String,
+    pub matter_id: String,
+    pub report_path: String,
+    pub subject: String,
+    pub recipient: Option<String>,
+    pub status: String,
+    pub reviewed_by: Option<String>,
+    pub review_note: Option<String>,
+    pub reviewed_at: Option<String>,
+    pub sent_at: Option<String>,
+    pub delivery_error: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClientStatusReportsResponse {
+    pub reports: Vec<ClientStatusReportInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClientStatusReportDetailResponse {
+    pub report: ClientStatusReportInfo,
+    pub content: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClientStatusReportResponse {
+    pub report: ClientStatusReportInfo,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApproveClientStatusReportRequest {
+    pub attorney: String,
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Overrides the client email on file.
+    #[serde(default)]
+    pub recipient: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RejectClientStatusReportRequest {
+    pub attorney: String,
+    pub note: String,
+}
+
 #[derive(Debug, Serialize)]
 pub struct MatterDashboardResponse {
     pub matter_id: String,
@@ -1410,6 +2648,31 @@ pub struct MatterDashboardResponse {
     pub next_deadline: Option<MatterDeadlineInfo>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct MatterStorageResponse {
+    pub matter_id: String,
+    pub document_count: u64,
+    pub document_bytes: u64,
+    pub chunk_count: u64,
+    pub chunk_bytes: u64,
+    pub embedded_chunk_count: u64,
+    pub binary_count: u64,
+    pub binary_bytes: u64,
+    pub version_count: u64,
+    pub total_bytes: u64,
+    pub quota: MatterStorageQuotaInfo,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterStorageQuotaInfo {
+    pub status: crate::legal::storage::QuotaStatus,
+    pub soft_quota_bytes: Option<u64>,
+    pub warn_at_percent: u8,
+    pub percent_used: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub struct MatterDeadlineInfo {
     pub date: String,
@@ -1530,103 +2793,701 @@ pub struct MatterDeadlineComputeResponse {
 }
 
 #[derive(Debug, Serialize)]
-pub struct MatterDeadlineComputePreview {
+pub struct MatterDeadlineComputePreview {
+    pub title: String,
+    pub deadline_type: String,
+    pub due_at: String,
+    pub reminder_days: Vec<i32>,
+    pub rule_ref: Option<String>,
+    pub computed_from: Option<String>,
+    pub task_id: Option<String>,
+    pub is_overdue: bool,
+    pub days_until_due: i64,
+    /// Human-readable computation trace from the deadline engine.
+    pub explanation: serde_json::Value,
+}
+
+/// Request body for `POST /api/matters/{id}/deadlines/{deadline_id}/override`.
+#[derive(Debug, Deserialize)]
+pub struct DeadlineOverrideRequest {
+    /// New deadline date (RFC-3339 or `YYYY-MM-DD` format).
+    pub due_at: String,
+    /// Mandatory reason for the override (e.g. "Stipulated extension by counsel").
+    pub reason: String,
+}
+
+/// One entry in the deadline override audit trail.
+#[derive(Debug, Serialize)]
+pub struct DeadlineOverrideAuditInfo {
+    pub id: String,
+    pub deadline_id: String,
+    pub user_id: String,
+    pub previous_due_at: String,
+    pub new_due_at: String,
+    pub reason: String,
+    pub created_at: String,
+}
+
+/// Response for `GET /api/matters/{id}/deadlines/{deadline_id}/audit`.
+#[derive(Debug, Serialize)]
+pub struct DeadlineOverrideAuditResponse {
+    pub deadline_id: String,
+    pub entries: Vec<DeadlineOverrideAuditInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CourtRuleInfo {
+    pub id: String,
+    pub citation: String,
+    pub deadline_type: String,
+    pub offset_months: i64,
+    pub offset_days: i64,
+    pub court_days: bool,
+    pub version: String,
+    pub jurisdiction: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CourtRulesResponse {
+    pub rules: Vec<CourtRuleInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImmigrationFormInfo {
+    pub id: String,
+    pub title: String,
+    pub agency: String,
+    pub edition: String,
+    pub field_count: usize,
+    pub required_count: usize,
+    pub deadline_presets: Vec<CourtRuleInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImmigrationFormsResponse {
+    pub forms: Vec<ImmigrationFormInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterImmigrationFormResponse {
+    pub matter_id: String,
+    pub case_data_path: String,
+    pub form: crate::legal::immigration::FilledForm,
+    /// Rules to compute with `POST /api/matters/{id}/deadlines/compute`
+    /// once USCIS notices arrive.
+    pub deadline_presets: Vec<CourtRuleInfo>,
+}
+
+/// `format` is `json` (default) or `xfdf`. Incomplete forms are rejected
+/// unless `allow_incomplete` is set.
+#[derive(Debug, Deserialize)]
+pub struct ImmigrationFormExportRequest {
+    #[serde(default)]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub allow_incomplete: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImmigrationFormExportResponse {
+    pub matter_id: String,
+    pub form_id: String,
+    pub format: String,
+    pub path: String,
+    pub complete: bool,
+    pub missing: Vec<String>,
+    pub invalid: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IpOfficeInfo {
+    pub code: String,
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub rules: Vec<IpRuleInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IpRuleInfo {
+    pub rule: CourtRuleInfo,
+    pub right: String,
+    pub trigger: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grace_for: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IpRulesResponse {
+    pub offices: Vec<IpOfficeInfo>,
+}
+
+/// Request body for `POST /api/matters/{id}/deadlines/ip-docket`. Event
+/// dates accept RFC-3339 or `YYYY-MM-DD`; terms whose event is omitted are
+/// not docketed.
+#[derive(Debug, Deserialize)]
+pub struct IpDocketRequest {
+    /// Office code or country alias (`USPTO`, `US`, `EP`, `EU`, `GB`, `CA`).
+    pub office: String,
+    /// `patent` or `trademark`.
+    pub right: String,
+    #[serde(default)]
+    pub application_number: Option<String>,
+    #[serde(default)]
+    pub filing_date: Option<String>,
+    #[serde(default)]
+    pub priority_date: Option<String>,
+    #[serde(default)]
+    pub grant_date: Option<String>,
+    #[serde(default)]
+    pub registration_date: Option<String>,
+    #[serde(default)]
+    pub office_action_date: Option<String>,
+    #[serde(default)]
+    pub allowance_date: Option<String>,
+    /// Include Paris Convention and PCT terms (default true).
+    #[serde(default)]
+    pub include_treaty: Option<bool>,
+    #[serde(default)]
+    pub reminder_days: Vec<i32>,
+    /// If true, persist anchors and computed deadlines to the database.
+    #[serde(default)]
+    pub save: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IpDocketEntryInfo {
+    pub rule: CourtRuleInfo,
+    pub trigger: String,
+    pub trigger_date: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grace_for: Option<String>,
+    pub deadline: MatterDeadlineComputePreview,
+    /// Existing deadline already docketed for this rule from the same anchor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub existing_deadline_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IpDocketResponse {
+    pub matter_id: String,
+    pub office: String,
+    pub right: String,
+    pub application: String,
+    pub entries: Vec<IpDocketEntryInfo>,
+    /// Populated when `save = true`: one completed anchor per trigger event.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub anchors: Vec<MatterDeadlineRecordInfo>,
+    /// Populated when `save = true`: deadlines created by this request.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub saved: Vec<MatterDeadlineRecordInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterFilingPackageResponse {
+    pub matter_id: String,
+    pub path: String,
+    pub generated_at: String,
+    pub status: &'static str,
+    /// Pre-filing validation result; `None` without a database.
+    pub validation_passed: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterFilingValidationResponse {
+    pub matter_id: String,
+    pub jurisdiction: Option<String>,
+    #[serde(flatten)]
+    pub report: crate::legal::filing_validation::FilingValidationReport,
+}
+
+// --- Exhibits ---
+
+#[derive(Debug, Serialize)]
+pub struct MatterExhibitInfo {
+    pub id: String,
+    pub matter_id: String,
+    pub label: String,
+    pub party_prefix: String,
+    pub sequence: i32,
+    pub path: String,
+    pub matter_document_id: Option<String>,
+    pub description: Option<String>,
+    pub status: String,
+    pub cover_sheet_path: String,
+    pub created_by: String,
+    pub created_at: String,
+    pub withdrawn_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterExhibitsResponse {
+    pub matter_id: String,
+    pub exhibits: Vec<MatterExhibitInfo>,
+    pub exhibit_list_path: String,
+}
+
+/// Mark a matter document as an exhibit by `document_id` or workspace
+/// `path`.
+#[derive(Debug, Deserialize)]
+pub struct MarkExhibitRequest {
+    #[serde(default)]
+    pub document_id: Option<String>,
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Defaults to `P`.
+    #[serde(default)]
+    pub party_prefix: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterExhibitResponse {
+    pub exhibit: MatterExhibitInfo,
+    pub exhibit_list_path: String,
+}
+
+// --- Settlement ---
+
+#[derive(Debug, Serialize)]
+pub struct SettlementProposalInfo {
+    pub id: String,
+    pub matter_id: String,
+    pub kind: String,
+    pub side: String,
+    pub from_party: String,
+    pub to_party: Option<String>,
+    pub amount: Option<String>,
+    pub terms: Option<String>,
+    pub proposed_on: String,
+    pub expires_on: Option<String>,
+    pub status: String,
+    pub response_notes: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SettlementAuthorityInfo {
+    pub id: String,
+    pub matter_id: String,
+    pub kind: String,
+    pub amount: String,
+    pub granted_on: String,
+    pub granted_by: Option<String>,
+    pub notes: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatterSettlementResponse {
+    pub matter_id: String,
+    pub proposals: Vec<SettlementProposalInfo>,
+    pub position: crate::legal::settlement::NegotiationPosition,
+    /// Authority in force; `authority_history` lists every grant, newest
+    /// first.
+    pub authority: Option<SettlementAuthorityInfo>,
+    pub authority_history: Vec<SettlementAuthorityInfo>,
+    pub negotiation_history_path: String,
+}
+
+/// Record a demand or offer. `side` is `client` for proposals made on the
+/// client's behalf, which are checked against settlement authority.
+#[derive(Debug, Deserialize)]
+pub struct CreateSettlementProposalRequest {
+    pub kind: String,
+    pub side: String,
+    pub from_party: String,
+    #[serde(default)]
+    pub to_party: Option<String>,
+    #[serde(default)]
+    pub amount: Option<String>,
+    #[serde(default)]
+    pub terms: Option<String>,
+    /// Defaults to today.
+    #[serde(default)]
+    pub proposed_on: Option<String>,
+    #[serde(default)]
+    pub expires_on: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SettlementProposalResponse {
+    pub proposal: SettlementProposalInfo,
+    /// Set for client proposals with an amount when authority is on file.
+    pub authority_check: Option<crate::legal::settlement::AuthorityCheck>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSettlementProposalStatusRequest {
+    pub status: String,
+    #[serde(default)]
+    pub response_notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSettlementAuthorityRequest {
+    pub kind: String,
+    pub amount: String,
+    /// Defaults to today.
+    #[serde(default)]
+    pub granted_on: Option<String>,
+    #[serde(default)]
+    pub granted_by: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SettlementAuthorityCheckRequest {
+    pub amount: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SettlementAuthorityCheckResponse {
+    pub matter_id: String,
+    /// `None` when no authority is on file.
+    pub check: Option<crate::legal::settlement::AuthorityCheck>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SettlementHistoryExportResponse {
+    pub matter_id: String,
+    pub path: String,
+    pub generated_at: String,
+    pub proposal_count: usize,
+}
+
+// --- Precedent bank ---
+
+#[derive(Debug, Serialize)]
+pub struct PrecedentInfo {
+    pub id: String,
+    pub title: String,
+    pub path: String,
+    pub source_matter_id: String,
+    pub source_path: String,
+    pub document_type: Option<String>,
+    pub practice_area: Option<String>,
+    pub jurisdiction: Option<String>,
+    pub promoted_by: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PrecedentsResponse {
+    pub precedents: Vec<PrecedentInfo>,
+}
+
+/// Preview the anonymization of a matter document before promotion. Without
+/// `replacements` the proposed mapping is previewed; with them, the
+/// attorney's edited mapping is.
+#[derive(Debug, Deserialize)]
+pub struct AnonymizePrecedentRequest {
+    pub path: String,
+    #[serde(default)]
+    pub replacements: Option<Vec<crate::legal::precedent::Replacement>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AnonymizePrecedentResponse {
+    pub matter_id: String,
+    pub path: String,
+    /// Mapping proposed from the client name, entity index, and addresses.
+    pub proposed: Vec<crate::legal::anonymize::ProposedReplacement>,
+    /// Mapping the preview and `review_token` cover.
+    pub replacements: Vec<crate::legal::precedent::Replacement>,
+    pub substitutions: usize,
+    /// The document body as it would be promoted.
+    pub preview: String,
+    pub review_token: String,
+}
+
+/// Promote a document from a closed matter into the precedent bank. The
+/// client's name is always replaced; `replacements` is the mapping reviewed
+/// through the anonymization preview, and `review_token` the token that
+/// preview returned for it.
+#[derive(Debug, Deserialize)]
+pub struct PromotePrecedentRequest {
+    pub path: String,
+    /// Defaults to the document's file name.
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub document_type: Option<String>,
+    #[serde(default)]
+    pub replacements: Vec<crate::legal::precedent::Replacement>,
+    #[serde(default)]
+    pub review_token: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PromotePrecedentResponse {
+    pub precedent: PrecedentInfo,
+    pub substitutions: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PrecedentSuggestQuery {
+    pub q: String,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PrecedentSuggestResponse {
+    pub matter_id: String,
+    pub suggestions: Vec<crate::legal::precedent::PrecedentSuggestion>,
+}
+
+// --- Memory upload ---
+
+/// One successfully uploaded file entry in the upload response.
+#[derive(Debug, Serialize)]
+pub struct UploadedFile {
+    pub path: String,
+    pub bytes: usize,
+    pub status: &'static str,
+    /// Assigned category when the upload targeted a matter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<crate::db::MatterDocumentCategory>,
+    /// Matter-relative folder the file was filed under.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_folder: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub classified_by: Option<crate::legal::classify::ClassificationSource>,
+    /// Extracted people/organizations queued for conflict review.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub party_candidates: Option<usize>,
+    /// Page/line structure found when uploaded in transcript mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcript: Option<UploadedTranscriptInfo>,
+}
+
+/// Parsed deposition transcript summary in the upload response.
+#[derive(Debug, Serialize)]
+pub struct UploadedTranscriptInfo {
+    pub witness: Option<String>,
+    /// Citation prefix, e.g. `Smith Dep.`.
+    pub citation: Option<String>,
+    pub first_page: u32,
+    pub last_page: u32,
+    pub pages: usize,
+    pub lines: usize,
+}
+
+/// Response body returned by `GET /api/memory/embedding-status`.
+#[derive(Debug, Serialize)]
+pub struct EmbeddingStatusResponse {
+    pub enabled: bool,
+    pub model: Option<String>,
+    pub total_chunks: u64,
+    pub embedded_chunks: u64,
+    pub pending_chunks: u64,
+    /// Share of chunks with embeddings, 0-100 (100 when there are no chunks).
+    pub percent_complete: f64,
+    pub backfill: crate::workspace::backfill::EmbeddingBackfillProgress,
+}
+
+/// Response body returned by `POST /api/memory/upload`.
+#[derive(Debug, Serialize)]
+pub struct MemoryUploadResponse {
+    pub files: Vec<UploadedFile>,
+}
+
+/// An attachment in an [`InboundEmailRequest`].
+#[derive(Debug, Deserialize)]
+pub struct InboundEmailAttachment {
+    pub filename: String,
+    #[serde(default)]
+    pub content_type: Option<String>,
+    /// Base64-encoded file content.
+    pub content: String,
+}
+
+/// Request body for `POST /api/email/inbound/{token}`: one parsed email
+/// forwarded by the mail provider's inbound webhook.
+#[derive(Debug, Deserialize)]
+pub struct InboundEmailRequest {
+    pub from: String,
+    #[serde(default)]
+    pub to: Vec<String>,
+    #[serde(default)]
+    pub cc: Vec<String>,
+    /// Envelope recipients, when the provider reports them (covers Bcc).
+    #[serde(default)]
+    pub recipients: Vec<String>,
+    #[serde(default)]
+    pub subject: String,
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub message_id: Option<String>,
+    #[serde(default)]
+    pub attachments: Vec<InboundEmailAttachment>,
+}
+
+/// Response body returned by `POST /api/email/inbound/{token}`.
+#[derive(Debug, Serialize)]
+pub struct InboundEmailResponse {
+    /// False when no recipient or subject tag named an existing matter and
+    /// the message went to `email/unfiled/`.
+    pub filed: bool,
+    pub matter_id: Option<String>,
+    pub message_path: String,
+    pub attachments: Vec<String>,
+    pub skipped_attachments: Vec<String>,
+}
+
+// --- Jobs ---
+
+#[derive(Debug, Serialize)]
+pub struct JobInfo {
+    pub id: Uuid,
+    pub title: String,
+    pub state: String,
+    pub user_id: String,
+    pub created_at: String,
+    pub started_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JobListResponse {
+    pub jobs: Vec<JobInfo>,
+}
+
+/// A sandbox job holding or waiting for a container slot.
+#[derive(Debug, Serialize)]
+pub struct JobQueueEntryInfo {
+    pub job_id: Uuid,
     pub title: String,
-    pub deadline_type: String,
-    pub due_at: String,
-    pub reminder_days: Vec<i32>,
-    pub rule_ref: Option<String>,
-    pub computed_from: Option<String>,
-    pub task_id: Option<String>,
-    pub is_overdue: bool,
-    pub days_until_due: i64,
-    /// Human-readable computation trace from the deadline engine.
-    pub explanation: serde_json::Value,
+    pub state: String,
+    pub mode: String,
+    pub priority: String,
+    /// 1-based start order among all queued jobs; absent for running jobs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<usize>,
+    pub created_at: String,
+    pub started_at: Option<String>,
+    pub queued_at: Option<String>,
+    pub preemptions: u32,
+    /// 1-based attempt; above 1 after a retried failure.
+    pub attempt: u32,
+    /// Earliest start while waiting out a retry backoff.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_at: Option<String>,
 }
 
-/// Request body for `POST /api/matters/{id}/deadlines/{deadline_id}/override`.
-#[derive(Debug, Deserialize)]
-pub struct DeadlineOverrideRequest {
-    /// New deadline date (RFC-3339 or `YYYY-MM-DD` format).
-    pub due_at: String,
-    /// Mandatory reason for the override (e.g. "Stipulated extension by counsel").
-    pub reason: String,
+#[derive(Debug, Serialize)]
+pub struct JobQueueResponse {
+    pub max_concurrent_jobs: usize,
+    pub max_jobs_per_user: usize,
+    pub active_total: usize,
+    pub queued_total: usize,
+    /// Slots the caller is using, out of `max_jobs_per_user`.
+    pub user_active: usize,
+    pub active: Vec<JobQueueEntryInfo>,
+    pub queued: Vec<JobQueueEntryInfo>,
 }
 
-/// One entry in the deadline override audit trail.
+/// A sandbox job that exhausted its retry attempts.
 #[derive(Debug, Serialize)]
-pub struct DeadlineOverrideAuditInfo {
-    pub id: String,
-    pub deadline_id: String,
-    pub user_id: String,
-    pub previous_due_at: String,
-    pub new_due_at: String,
-    pub reason: String,
+pub struct DeadLetterJobInfo {
+    pub id: Uuid,
+    pub title: String,
+    pub failure_reason: Option<String>,
     pub created_at: String,
+    pub failed_at: Option<String>,
 }
 
-/// Response for `GET /api/matters/{id}/deadlines/{deadline_id}/audit`.
 #[derive(Debug, Serialize)]
-pub struct DeadlineOverrideAuditResponse {
-    pub deadline_id: String,
-    pub entries: Vec<DeadlineOverrideAuditInfo>,
+pub struct DeadLetterJobsResponse {
+    pub jobs: Vec<DeadLetterJobInfo>,
 }
 
+// --- Firm dashboard ---
+
 #[derive(Debug, Serialize)]
-pub struct CourtRuleInfo {
-    pub id: String,
-    pub citation: String,
-    pub deadline_type: String,
-    pub offset_days: i64,
-    pub court_days: bool,
-    pub version: String,
-    pub jurisdiction: String,
+pub struct DashboardDeadlineInfo {
+    pub matter_id: String,
+    pub deadline: MatterDeadlineRecordInfo,
 }
 
 #[derive(Debug, Serialize)]
-pub struct CourtRulesResponse {
-    pub rules: Vec<CourtRuleInfo>,
+pub struct DashboardTaskInfo {
+    pub matter_id: String,
+    pub days_overdue: i64,
+    pub task: MatterTaskInfo,
 }
 
 #[derive(Debug, Serialize)]
-pub struct MatterFilingPackageResponse {
+pub struct DashboardUnbilledInfo {
     pub matter_id: String,
-    pub path: String,
-    pub generated_at: String,
-    pub status: &'static str,
+    pub unbilled_hours: String,
 }
 
-// --- Memory upload ---
-
-/// One successfully uploaded file entry in the upload response.
 #[derive(Debug, Serialize)]
-pub struct UploadedFile {
-    pub path: String,
-    pub bytes: usize,
-    pub status: &'static str,
+pub struct BrokenToolInfo {
+    pub name: String,
+    pub failure_count: u32,
+    pub last_error: Option<String>,
+    pub last_failure: String,
+    pub repair_attempts: u32,
 }
 
-/// Response body returned by `POST /api/memory/upload`.
+/// `GET /api/dashboard`: practice-wide overview across open matters.
 #[derive(Debug, Serialize)]
-pub struct MemoryUploadResponse {
-    pub files: Vec<UploadedFile>,
+pub struct FirmDashboardResponse {
+    pub generated_at: String,
+    /// Matters that are not closed or archived.
+    pub matter_count: usize,
+    /// Incomplete deadlines due in the next 14 days, soonest first.
+    pub upcoming_deadlines: Vec<DashboardDeadlineInfo>,
+    /// Open tasks past their due date, most overdue first.
+    pub overdue_tasks: Vec<DashboardTaskInfo>,
+    pub unbilled_hours: String,
+    pub unbilled_by_matter: Vec<DashboardUnbilledInfo>,
+    pub active_jobs: Vec<JobInfo>,
+    pub broken_tools: Vec<BrokenToolInfo>,
 }
 
-// --- Jobs ---
+// --- Global search ---
+
+/// Query for `GET /api/search`.
+#[derive(Debug, Default, Deserialize)]
+pub struct GlobalSearchQuery {
+    #[serde(default)]
+    pub q: String,
+    /// Comma-separated result types to include (default: all).
+    #[serde(default)]
+    pub types: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
 
 #[derive(Debug, Serialize)]
-pub struct JobInfo {
-    pub id: Uuid,
+pub struct GlobalSearchHit {
+    /// `matter`, `client`, `document`, `task`, `note`, `deadline`, or `time_entry`.
+    pub kind: String,
+    /// Record ID, or the workspace path for documents.
+    pub id: String,
+    pub matter_id: Option<String>,
     pub title: String,
-    pub state: String,
-    pub user_id: String,
-    pub created_at: String,
-    pub started_at: Option<String>,
+    pub snippet: Option<String>,
+    pub path: Option<String>,
+    pub score: f64,
 }
 
 #[derive(Debug, Serialize)]
-pub struct JobListResponse {
-    pub jobs: Vec<JobInfo>,
+pub struct GlobalSearchResponse {
+    pub query: String,
+    /// Matches per kind before `limit` is applied.
+    pub facets: std::collections::BTreeMap<String, usize>,
+    pub total: usize,
+    pub results: Vec<GlobalSearchHit>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1639,6 +3500,38 @@ pub struct JobSummaryResponse {
     pub stuck: usize,
 }
 
+/// How estimated cost or time compares to actuals for one category.
+#[derive(Debug, Serialize)]
+pub struct EstimationMetricInfo {
+    /// Mean absolute error of the raw plan estimates, relative to actuals.
+    pub raw_error_rate: f64,
+    /// The same error after the learned correction is applied.
+    pub corrected_error_rate: f64,
+    /// Learned correction `actual = intercept + slope * estimate`; absent
+    /// until the category has enough samples.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slope: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intercept: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r_squared: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EstimationCategoryAccuracyInfo {
+    pub category: String,
+    pub sample_count: usize,
+    pub cost: EstimationMetricInfo,
+    pub time: EstimationMetricInfo,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EstimationAccuracyResponse {
+    /// Samples a category needs before its estimates are corrected.
+    pub min_samples: usize,
+    pub categories: Vec<EstimationCategoryAccuracyInfo>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct JobDetailResponse {
     pub id: Uuid,
@@ -1659,24 +3552,35 @@ pub struct JobDetailResponse {
     pub transitions: Vec<TransitionInfo>,
 }
 
-// --- Project Files ---
+// --- Job artifacts ---
 
 #[derive(Debug, Serialize)]
-pub struct ProjectFileEntry {
+pub struct JobArtifactInfo {
+    /// Path relative to the job's project directory.
     pub name: String,
-    pub path: String,
-    pub is_dir: bool,
+    pub size_bytes: i64,
+    pub content_type: String,
+    pub created_at: String,
+    /// Workspace path the artifact was promoted to, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub promoted_to: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
-pub struct ProjectFilesResponse {
-    pub entries: Vec<ProjectFileEntry>,
+pub struct JobArtifactsResponse {
+    pub job_id: Uuid,
+    pub artifacts: Vec<JobArtifactInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PromoteJobArtifactRequest {
+    pub matter_id: String,
 }
 
 #[derive(Debug, Serialize)]
-pub struct ProjectFileReadResponse {
-    pub path: String,
-    pub content: String,
+pub struct PromoteJobArtifactResponse {
+    pub matter_id: String,
+    pub document: MatterDocumentInfo,
 }
 
 #[derive(Debug, Serialize)]
@@ -1918,6 +3822,24 @@ pub enum WsClientMessage {
     /// Cancel an in-progress auth flow.
     #[serde(rename = "auth_cancel")]
     AuthCancel { extension_name: String },
+    /// Receive live note events for a matter.
+    #[serde(rename = "subscribe_matter")]
+    SubscribeMatter { matter_id: String },
+    /// Stop receiving live note events for a matter.
+    #[serde(rename = "unsubscribe_matter")]
+    UnsubscribeMatter { matter_id: String },
+    /// Receive the output stream of a sandbox job.
+    #[serde(rename = "subscribe_job")]
+    SubscribeJob { job_id: String },
+    /// Stop receiving a sandbox job's output stream.
+    #[serde(rename = "unsubscribe_job")]
+    UnsubscribeJob { job_id: String },
+    /// Typing indicator, relayed to the user's other connections.
+    #[serde(rename = "typing")]
+    Typing {
+        thread_id: Option<String>,
+        active: bool,
+    },
     /// Client heartbeat ping.
     #[serde(rename = "ping")]
     Ping,
@@ -1960,11 +3882,16 @@ impl WsServerMessage {
             SseEvent::AuthCompleted { .. } => "auth_completed",
             SseEvent::Error { .. } => "error",
             SseEvent::Heartbeat => "heartbeat",
+            SseEvent::Typing { .. } => "typing",
             SseEvent::JobMessage { .. } => "job_message",
             SseEvent::JobToolUse { .. } => "job_tool_use",
             SseEvent::JobToolResult { .. } => "job_tool_result",
             SseEvent::JobStatus { .. } => "job_status",
             SseEvent::JobResult { .. } => "job_result",
+            SseEvent::MatterNote { .. } => "matter_note",
+            SseEvent::MatterNoteMention { .. } => "matter_note_mention",
+            SseEvent::Domain { .. } => "domain_event",
+            SseEvent::Notification(_) => "notification",
         };
         let data = serde_json::to_value(event).unwrap_or(serde_json::Value::Null);
         WsServerMessage::Event {
@@ -1990,6 +3917,7 @@ pub struct RoutineInfo {
     pub run_count: u64,
     pub consecutive_failures: u32,
     pub status: String,
+    pub urgent: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -2013,9 +3941,13 @@ pub struct RoutineDetailResponse {
     pub description: String,
     pub enabled: bool,
     pub trigger: serde_json::Value,
+    /// URL path that fires a webhook-triggered routine, including its token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_path: Option<String>,
     pub action: serde_json::Value,
     pub guardrails: serde_json::Value,
     pub notify: serde_json::Value,
+    pub chain: serde_json::Value,
     pub last_run_at: Option<String>,
     pub next_fire_at: Option<String>,
     pub run_count: u64,
@@ -2041,15 +3973,27 @@ pub struct RoutineCreateRequest {
     pub description: Option<String>,
     // Trigger
     pub trigger_type: String,
+    /// Cron expression or plain-English schedule ("every weekday at 9am").
     pub schedule: Option<String>,
+    /// IANA timezone the schedule is read in (default UTC).
+    pub timezone: Option<String>,
+    pub jitter_secs: Option<u64>,
     pub event_pattern: Option<String>,
     pub event_channel: Option<String>,
     // Action
     pub action_type: Option<String>,
     pub prompt: String,
     pub context_paths: Option<Vec<String>>,
+    // Matter status report action
+    pub matter_id: Option<String>,
+    pub period_days: Option<u32>,
+    pub send: Option<bool>,
     // Guardrails
     pub cooldown_secs: Option<u64>,
+    pub urgent: Option<bool>,
+    pub execution: Option<ExecutionOverride>,
+    /// Routines fired when this one finishes.
+    pub chain: Option<RoutineChain>,
 }
 
 // --- Settings ---
@@ -2081,6 +4025,12 @@ pub struct SkepticalModeResolvedResponse {
     pub enabled: bool,
 }
 
+/// Body of `GET` and `PUT /api/settings/custom-fields`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CustomFieldSchemasPayload {
+    pub schemas: serde_json::Value,
+}
+
 // --- RBAC / Membership ---
 
 #[derive(Debug, Serialize)]
@@ -2119,11 +4069,247 @@ pub struct UserResponse {
     pub updated_at: String,
 }
 
+// --- Organizations ---
+
+#[derive(Debug, Deserialize)]
+pub struct CreateOrganizationRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrganizationResponse {
+    pub id: String,
+    pub name: String,
+    pub created_by: String,
+    /// The requesting user's role: "owner" | "admin" | "member" | "viewer"
+    pub role: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrganizationsListResponse {
+    pub organizations: Vec<OrganizationResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrganizationMemberResponse {
+    pub user_id: String,
+    /// "owner" | "admin" | "member" | "viewer"
+    pub role: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrganizationMembersListResponse {
+    pub org_id: String,
+    pub members: Vec<OrganizationMemberResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpsertOrganizationMemberRequest {
+    /// "owner" | "admin" | "member" | "viewer"
+    pub role: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrganizationMatterResponse {
+    pub matter_owner_user_id: String,
+    pub matter_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrganizationMattersListResponse {
+    pub org_id: String,
+    pub matters: Vec<OrganizationMatterResponse>,
+}
+
+/// Body for sharing a matter or client; `null` makes it private again.
+#[derive(Debug, Deserialize)]
+pub struct SetOrganizationRequest {
+    pub org_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SetOrganizationResponse {
+    pub org_id: Option<String>,
+    /// Workspace documents re-tagged (matter sharing only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documents_updated: Option<u64>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct SettingsExportResponse {
     pub settings: std::collections::HashMap<String, serde_json::Value>,
 }
 
+// --- Outbound review ---
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OutboundDraftInfo {
+    pub id: String,
+    /// "reply" or "broadcast".
+    pub kind: &'static str,
+    pub channel: String,
+    pub recipient: String,
+    pub thread_id: Option<String>,
+    pub content: String,
+    pub original_content: String,
+    pub edited: bool,
+    pub status: &'static str,
+    pub created_at: String,
+    pub decided_at: Option<String>,
+    pub decided_by: Option<String>,
+    pub rejection_reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OutboundDraftListResponse {
+    pub supervised_mode: bool,
+    pub pending: usize,
+    pub drafts: Vec<OutboundDraftInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OutboundDraftListQuery {
+    /// Filter by status; defaults to pending drafts.
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OutboundDraftEditRequest {
+    pub content: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct OutboundDraftApproveRequest {
+    /// Final content to send; the draft's current content when omitted.
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct OutboundDraftRejectRequest {
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+// --- Outbound webhooks ---
+
+#[derive(Debug, Serialize)]
+pub struct WebhookEndpointInfo {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub events: Vec<String>,
+    pub enabled: bool,
+    pub secret_hint: String,
+    /// Full signing secret; only present on create and secret rotation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookEndpointListResponse {
+    pub endpoints: Vec<WebhookEndpointInfo>,
+    /// Event kinds endpoints can subscribe to.
+    pub available_events: Vec<&'static str>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookEndpointRequest {
+    pub name: String,
+    pub url: String,
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct UpdateWebhookEndpointRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub events: Option<Vec<String>>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// Issue a new signing secret; the old one stops working immediately.
+    #[serde(default)]
+    pub rotate_secret: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDeliveryListResponse {
+    pub endpoint_id: String,
+    pub deliveries: Vec<crate::integrations::WebhookDelivery>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookTestResponse {
+    pub delivery_id: String,
+}
+
+// --- Notifications ---
+
+/// Messaging channel a user's notifications are also delivered on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationDeliveryInfo {
+    pub channel: String,
+    /// Address on that channel; the user ID when unset.
+    #[serde(default)]
+    pub recipient: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotificationInfo {
+    pub id: String,
+    /// "routine", "deadline", "system", or "mention".
+    pub kind: &'static str,
+    /// "info", "warn", or "critical".
+    pub severity: &'static str,
+    pub title: String,
+    pub body: String,
+    pub matter_id: Option<String>,
+    pub source: Option<String>,
+    pub read: bool,
+    pub acked: bool,
+    pub snoozed_until: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationListResponse {
+    pub notifications: Vec<NotificationInfo>,
+    pub unread_count: i64,
+    /// Unread notifications per kind; kinds with none are omitted.
+    pub unread_by_kind: std::collections::BTreeMap<&'static str, i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationListQuery {
+    /// "active" (default), "unread", "snoozed", "acked", or "all".
+    pub status: Option<String>,
+    pub kind: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct NotificationSnoozeRequest {
+    /// Snooze for this many minutes from now.
+    #[serde(default)]
+    pub minutes: Option<i64>,
+    /// Snooze until this RFC 3339 timestamp.
+    #[serde(default)]
+    pub until: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationReadAllResponse {
+    pub marked_read: u64,
+}
+
 // --- Health ---
 
 #[derive(Debug, Serialize)]
@@ -2240,6 +4426,7 @@ mod tests {
         let sse = SseEvent::Response {
             content: "hello".to_string(),
             thread_id: "t1".to_string(),
+            citations: Vec::new(),
         };
         let ws = WsServerMessage::from_sse_event(&sse);
         match ws {
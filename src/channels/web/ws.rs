@@ -1,7 +1,8 @@
 //! WebSocket handler for bidirectional client communication.
 //!
-//! Provides the same event stream as SSE but also accepts incoming messages
-//! (chat, approvals) over a single persistent connection.
+//! Provides the same event stream as SSE but also accepts commands (chat,
+//! approvals, subscriptions, typing indicators) over a single persistent
+//! connection, so a client does not need REST + SSE alongside it.
 //!
 //! ```text
 //! Client ──── WS frame: {"type":"message","content":"hello"} ──► Agent Loop
@@ -9,19 +10,40 @@
 //!        ──── WS frame: {"type":"ping"} ──────────────────────────────────────►
 //!        ◄─── WS frame: {"type":"pong"} ──────────────────────────────────────
 //! ```
+//!
+//! Matter note events are only forwarded for matters the client has
+//! subscribed to with `{"type":"subscribe_matter","matter_id":"..."}`, which
+//! requires viewer access to the matter for the connection's principal, and
+//! sandbox job output only for jobs subscribed to with
+//! `{"type":"subscribe_job","job_id":"..."}`.
+//!
+//! The server sends a protocol-level ping every [`PING_INTERVAL`] and drops
+//! connections that send nothing (frames, pongs, or pings) for
+//! [`IDLE_TIMEOUT`].
 
-use std::sync::Arc;
+use std::collections::HashSet;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 use axum::extract::ws::{Message, WebSocket};
+use axum::http::StatusCode;
 use futures::{SinkExt, StreamExt};
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use crate::agent::submission::Submission;
 use crate::channels::IncomingMessage;
+use crate::channels::web::handlers::helpers::matter::require_matter_access;
 use crate::channels::web::state::GatewayState;
-use crate::channels::web::types::{WsClientMessage, WsServerMessage};
+use crate::channels::web::types::{SseEvent, WsClientMessage, WsServerMessage};
+use crate::db::MatterMemberRole;
+
+/// How often the server pings an otherwise quiet connection.
+pub const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a connection may stay silent before it is closed.
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(90);
 
 /// Tracks active WebSocket connections.
 pub struct WsConnectionTracker {
@@ -54,15 +76,62 @@ impl Default for WsConnectionTracker {
     }
 }
 
+/// Per-connection state shared between the sender and receiver tasks.
+#[derive(Debug, Default)]
+struct ConnectionState {
+    /// Identifies this connection in the typing events it relays.
+    id: String,
+    /// User the connection authenticated as; matter subscriptions are
+    /// checked against this user's access.
+    principal_user_id: String,
+    /// Matters this connection receives note events for.
+    matters: HashSet<String>,
+    /// Sandbox jobs this connection receives output for.
+    jobs: HashSet<String>,
+}
+
+type Subscriptions = Arc<RwLock<ConnectionState>>;
+
+fn new_subscriptions(principal_user_id: &str) -> Subscriptions {
+    Arc::new(RwLock::new(ConnectionState {
+        id: Uuid::new_v4().to_string(),
+        principal_user_id: principal_user_id.to_string(),
+        ..Default::default()
+    }))
+}
+
+/// Whether a broadcast event should reach a connection with these
+/// subscriptions. Events not scoped to a matter or job always pass, except
+/// typing indicators the connection sent itself.
+fn is_subscribed(subscriptions: &Subscriptions, event: &SseEvent) -> bool {
+    let Ok(conn) = subscriptions.read() else {
+        return false;
+    };
+    if let Some(matter_id) = event.matter_id() {
+        return conn.matters.contains(matter_id);
+    }
+    if let Some(job_id) = event.job_id() {
+        return conn.jobs.contains(job_id);
+    }
+    match event {
+        SseEvent::Typing { origin, .. } => origin.as_deref() != Some(conn.id.as_str()),
+        _ => true,
+    }
+}
+
 /// Handle an upgraded WebSocket connection.
 ///
 /// Spawns two tasks:
 /// - **sender**: forwards broadcast events to the WebSocket client
 /// - **receiver**: reads client frames and routes them to the agent
 ///
-/// When either task ends (client disconnect or broadcast closed), both are
-/// cleaned up.
-pub async fn handle_ws_connection(socket: WebSocket, state: Arc<GatewayState>) {
+/// When either task ends (client disconnect, idle timeout, or broadcast
+/// closed), both are cleaned up.
+pub async fn handle_ws_connection(
+    socket: WebSocket,
+    state: Arc<GatewayState>,
+    principal_user_id: String,
+) {
     let (mut ws_sink, mut ws_stream) = socket.split();
 
     // Track connection
@@ -86,14 +155,28 @@ pub async fn handle_ws_connection(socket: WebSocket, state: Arc<GatewayState>) {
     // Channel for the sender task to receive messages from both
     // the broadcast stream and any direct sends (like Pong)
     let (direct_tx, mut direct_rx) = mpsc::channel::<WsServerMessage>(64);
+    let subscriptions = new_subscriptions(&principal_user_id);
+    let sender_subscriptions = Arc::clone(&subscriptions);
 
-    // Sender task: forward broadcast events + direct messages to WS client
+    // Sender task: forward broadcast events + direct messages to WS client,
+    // pinging periodically so idle clients prove they are still there.
     let sender_handle = tokio::spawn(async move {
+        let mut ping =
+            tokio::time::interval_at(tokio::time::Instant::now() + PING_INTERVAL, PING_INTERVAL);
         loop {
             let msg = tokio::select! {
+                _ = ping.tick() => {
+                    if ws_sink.send(Message::Ping(Vec::new().into())).await.is_err() {
+                        break;
+                    }
+                    continue;
+                }
                 event = event_stream.next() => {
                     match event {
-                        Some(sse_event) => WsServerMessage::from_sse_event(&sse_event),
+                        Some(sse_event) if is_subscribed(&sender_subscriptions, &sse_event) => {
+                            WsServerMessage::from_sse_event(&sse_event)
+                        }
+                        Some(_) => continue,
                         None => break, // Broadcast channel closed
                     }
                 }
@@ -118,13 +201,28 @@ pub async fn handle_ws_connection(socket: WebSocket, state: Arc<GatewayState>) {
 
     // Receiver task: read client frames and route to agent
     let user_id = state.user_id.clone();
-    while let Some(Ok(frame)) = ws_stream.next().await {
+    loop {
+        let frame = match tokio::time::timeout(IDLE_TIMEOUT, ws_stream.next()).await {
+            Ok(Some(Ok(frame))) => frame,
+            Ok(_) => break,
+            Err(_) => {
+                tracing::debug!("WebSocket closed after {:?} without traffic", IDLE_TIMEOUT);
+                break;
+            }
+        };
         match frame {
             Message::Text(text) => {
                 let parsed: Result<WsClientMessage, _> = serde_json::from_str(&text);
                 match parsed {
                     Ok(client_msg) => {
-                        handle_client_message(client_msg, &state, &user_id, &direct_tx).await;
+                        handle_client_message(
+                            client_msg,
+                            &state,
+                            &user_id,
+                            &subscriptions,
+                            &direct_tx,
+                        )
+                        .await;
                     }
                     Err(e) => {
                         let _ = direct_tx
@@ -136,7 +234,8 @@ pub async fn handle_ws_connection(socket: WebSocket, state: Arc<GatewayState>) {
                 }
             }
             Message::Close(_) => break,
-            // Ignore binary, ping/pong (axum handles protocol-level pings)
+            // Binary and ping/pong frames only count as activity (axum
+            // answers protocol-level pings itself).
             _ => {}
         }
     }
@@ -153,6 +252,7 @@ async fn handle_client_message(
     msg: WsClientMessage,
     state: &GatewayState,
     user_id: &str,
+    subscriptions: &Subscriptions,
     direct_tx: &mpsc::Sender<WsServerMessage>,
 ) {
     match msg {
@@ -162,19 +262,10 @@ async fn handle_client_message(
                 incoming = incoming.with_thread(tid);
             }
 
-            let tx_guard = state.msg_tx.read().await;
-            if let Some(ref tx) = *tx_guard {
-                if tx.send(incoming).await.is_err() {
-                    let _ = direct_tx
-                        .send(WsServerMessage::Error {
-                            message: "Channel closed".to_string(),
-                        })
-                        .await;
-                }
-            } else {
+            if let Err(err) = state.enqueue_message(incoming).await {
                 let _ = direct_tx
                     .send(WsServerMessage::Error {
-                        message: "Channel not started".to_string(),
+                        message: err.to_string(),
                     })
                     .await;
             }
@@ -231,9 +322,12 @@ async fn handle_client_message(
             if let Some(ref tid) = thread_id {
                 msg = msg.with_thread(tid);
             }
-            let tx_guard = state.msg_tx.read().await;
-            if let Some(ref tx) = *tx_guard {
-                let _ = tx.send(msg).await;
+            if let Err(err) = state.enqueue_message(msg).await {
+                let _ = direct_tx
+                    .send(WsServerMessage::Error {
+                        message: err.to_string(),
+                    })
+                    .await;
             }
         }
         WsClientMessage::AuthToken {
@@ -292,6 +386,74 @@ async fn handle_client_message(
         WsClientMessage::AuthCancel { .. } => {
             crate::channels::web::server::clear_auth_mode(state).await;
         }
+        WsClientMessage::SubscribeMatter { matter_id } => {
+            let matter_id = crate::legal::policy::sanitize_matter_id(&matter_id);
+            if matter_id.is_empty() {
+                let _ = direct_tx
+                    .send(WsServerMessage::Error {
+                        message: "Invalid matter_id".to_string(),
+                    })
+                    .await;
+                return;
+            }
+            let principal_user_id = subscriptions
+                .read()
+                .map(|conn| conn.principal_user_id.clone())
+                .unwrap_or_default();
+            if let Err(status) = require_matter_access(
+                &state.store,
+                &state.user_id,
+                &matter_id,
+                &principal_user_id,
+                MatterMemberRole::Viewer,
+            )
+            .await
+            {
+                let message = if status == StatusCode::FORBIDDEN {
+                    format!("Not allowed to subscribe to matter '{}'", matter_id)
+                } else {
+                    format!("Failed to check access to matter '{}'", matter_id)
+                };
+                let _ = direct_tx.send(WsServerMessage::Error { message }).await;
+                return;
+            }
+            if let Ok(mut conn) = subscriptions.write() {
+                conn.matters.insert(matter_id);
+            }
+        }
+        WsClientMessage::UnsubscribeMatter { matter_id } => {
+            let matter_id = crate::legal::policy::sanitize_matter_id(&matter_id);
+            if let Ok(mut conn) = subscriptions.write() {
+                conn.matters.remove(&matter_id);
+            }
+        }
+        WsClientMessage::SubscribeJob { job_id } => {
+            let job_id = job_id.trim();
+            if job_id.is_empty() {
+                let _ = direct_tx
+                    .send(WsServerMessage::Error {
+                        message: "Invalid job_id".to_string(),
+                    })
+                    .await;
+                return;
+            }
+            if let Ok(mut conn) = subscriptions.write() {
+                conn.jobs.insert(job_id.to_string());
+            }
+        }
+        WsClientMessage::UnsubscribeJob { job_id } => {
+            if let Ok(mut conn) = subscriptions.write() {
+                conn.jobs.remove(job_id.trim());
+            }
+        }
+        WsClientMessage::Typing { thread_id, active } => {
+            let origin = subscriptions.read().ok().map(|conn| conn.id.clone());
+            state.sse.broadcast(SseEvent::Typing {
+                thread_id,
+                active,
+                origin,
+            });
+        }
         WsClientMessage::Ping => {
             let _ = direct_tx.send(WsServerMessage::Pong).await;
         }
@@ -332,7 +494,14 @@ mod tests {
         let (direct_tx, mut direct_rx) = mpsc::channel(16);
         let state = make_test_state(None).await;
 
-        handle_client_message(WsClientMessage::Ping, &state, "user1", &direct_tx).await;
+        handle_client_message(
+            WsClientMessage::Ping,
+            &state,
+            "user1",
+            &new_subscriptions("test"),
+            &direct_tx,
+        )
+        .await;
 
         let response = direct_rx.recv().await.unwrap();
         assert!(matches!(response, WsServerMessage::Pong));
@@ -352,6 +521,7 @@ mod tests {
             },
             &state,
             "user1",
+            &new_subscriptions("test"),
             &direct_tx,
         )
         .await;
@@ -376,6 +546,7 @@ mod tests {
             },
             &state,
             "user1",
+            &new_subscriptions("test"),
             &direct_tx,
         )
         .await;
@@ -404,6 +575,7 @@ mod tests {
             },
             &state,
             "user1",
+            &new_subscriptions("test"),
             &direct_tx,
         )
         .await;
@@ -428,6 +600,7 @@ mod tests {
             },
             &state,
             "user1",
+            &new_subscriptions("test"),
             &direct_tx,
         )
         .await;
@@ -454,6 +627,7 @@ mod tests {
             },
             &state,
             "user1",
+            &new_subscriptions("test"),
             &direct_tx,
         )
         .await;
@@ -467,12 +641,193 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_matter_subscriptions_filter_note_events() {
+        let state = make_test_state(None).await;
+        let (direct_tx, _direct_rx) = mpsc::channel(16);
+        let subscriptions = new_subscriptions("test");
+        let note_event = |matter_id: &str| SseEvent::MatterNote {
+            matter_id: matter_id.to_string(),
+            action: "created".to_string(),
+            note_id: Uuid::new_v4().to_string(),
+            actor: "alice".to_string(),
+        };
+
+        assert!(!is_subscribed(&subscriptions, &note_event("acme-v-foo")));
+        assert!(is_subscribed(&subscriptions, &SseEvent::Heartbeat));
+
+        handle_client_message(
+            WsClientMessage::SubscribeMatter {
+                matter_id: "acme-v-foo".to_string(),
+            },
+            &state,
+            "user1",
+            &subscriptions,
+            &direct_tx,
+        )
+        .await;
+        assert!(is_subscribed(&subscriptions, &note_event("acme-v-foo")));
+        assert!(!is_subscribed(&subscriptions, &note_event("other-matter")));
+
+        handle_client_message(
+            WsClientMessage::UnsubscribeMatter {
+                matter_id: "acme-v-foo".to_string(),
+            },
+            &state,
+            "user1",
+            &subscriptions,
+            &direct_tx,
+        )
+        .await;
+        assert!(!is_subscribed(&subscriptions, &note_event("acme-v-foo")));
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn test_subscribe_matter_requires_matter_access() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let mut state = make_test_state(None).await;
+        state.store = Some(db);
+        let (direct_tx, mut direct_rx) = mpsc::channel(16);
+        let note_event = SseEvent::MatterNote {
+            matter_id: "acme-v-foo".to_string(),
+            action: "created".to_string(),
+            note_id: Uuid::new_v4().to_string(),
+            actor: "test".to_string(),
+        };
+
+        let outsider = new_subscriptions("mallory");
+        handle_client_message(
+            WsClientMessage::SubscribeMatter {
+                matter_id: "acme-v-foo".to_string(),
+            },
+            &state,
+            "test",
+            &outsider,
+            &direct_tx,
+        )
+        .await;
+        match direct_rx.recv().await.unwrap() {
+            WsServerMessage::Error { message } => assert!(message.contains("Not allowed")),
+            _ => panic!("Expected Error variant"),
+        }
+        assert!(!is_subscribed(&outsider, &note_event));
+
+        let owner = new_subscriptions("test");
+        handle_client_message(
+            WsClientMessage::SubscribeMatter {
+                matter_id: "acme-v-foo".to_string(),
+            },
+            &state,
+            "test",
+            &owner,
+            &direct_tx,
+        )
+        .await;
+        assert!(direct_rx.try_recv().is_err());
+        assert!(is_subscribed(&owner, &note_event));
+    }
+
+    #[tokio::test]
+    async fn test_job_subscriptions_filter_job_output() {
+        let state = make_test_state(None).await;
+        let (direct_tx, _direct_rx) = mpsc::channel(16);
+        let subscriptions = new_subscriptions("test");
+        let status = |job_id: &str| SseEvent::JobStatus {
+            job_id: job_id.to_string(),
+            message: "running".to_string(),
+        };
+        let started = SseEvent::JobStarted {
+            job_id: "job-1".to_string(),
+            title: "Research".to_string(),
+            browse_url: "/jobs/job-1".to_string(),
+        };
+
+        assert!(!is_subscribed(&subscriptions, &status("job-1")));
+        assert!(is_subscribed(&subscriptions, &started));
+
+        handle_client_message(
+            WsClientMessage::SubscribeJob {
+                job_id: "job-1".to_string(),
+            },
+            &state,
+            "user1",
+            &subscriptions,
+            &direct_tx,
+        )
+        .await;
+        assert!(is_subscribed(&subscriptions, &status("job-1")));
+        assert!(!is_subscribed(&subscriptions, &status("job-2")));
+
+        handle_client_message(
+            WsClientMessage::UnsubscribeJob {
+                job_id: "job-1".to_string(),
+            },
+            &state,
+            "user1",
+            &subscriptions,
+            &direct_tx,
+        )
+        .await;
+        assert!(!is_subscribed(&subscriptions, &status("job-1")));
+    }
+
+    #[tokio::test]
+    async fn test_typing_is_relayed_to_other_connections_only() {
+        let state = make_test_state(None).await;
+        let (direct_tx, _direct_rx) = mpsc::channel(16);
+        let mut events = Box::pin(state.sse.subscribe_raw().unwrap());
+        let sender = new_subscriptions("test");
+        let other = new_subscriptions("test");
+
+        handle_client_message(
+            WsClientMessage::Typing {
+                thread_id: Some("t1".to_string()),
+                active: true,
+            },
+            &state,
+            "user1",
+            &sender,
+            &direct_tx,
+        )
+        .await;
+
+        let event = events.next().await.unwrap();
+        assert!(matches!(
+            event,
+            SseEvent::Typing { ref thread_id, active: true, .. } if thread_id.as_deref() == Some("t1")
+        ));
+        assert!(!is_subscribed(&sender, &event));
+        assert!(is_subscribed(&other, &event));
+
+        let wire = serde_json::to_value(&event).unwrap();
+        assert_eq!(wire["type"], "typing");
+        assert!(wire.get("origin").is_none());
+    }
+
+    #[test]
+    fn test_client_protocol_parses_new_commands() {
+        let msg: WsClientMessage =
+            serde_json::from_str(r#"{"type":"subscribe_job","job_id":"abc"}"#).unwrap();
+        assert!(matches!(msg, WsClientMessage::SubscribeJob { job_id } if job_id == "abc"));
+        let msg: WsClientMessage =
+            serde_json::from_str(r#"{"type":"typing","thread_id":null,"active":false}"#).unwrap();
+        assert!(matches!(
+            msg,
+            WsClientMessage::Typing {
+                thread_id: None,
+                active: false
+            }
+        ));
+    }
+
     /// Helper to create a GatewayState for testing.
     async fn make_test_state(msg_tx: Option<mpsc::Sender<IncomingMessage>>) -> GatewayState {
         use crate::channels::web::sse::SseManager;
 
         GatewayState {
             msg_tx: tokio::sync::RwLock::new(msg_tx),
+            intake: Arc::new(crate::channels::IntakeQueue::default()),
             sse: SseManager::new(),
             workspace: None,
             session_manager: None,
@@ -489,12 +844,18 @@ mod tests {
             llm_provider: None,
             skill_registry: None,
             skill_catalog: None,
-            chat_rate_limiter: crate::channels::web::state::RateLimiter::new(30, 60),
+            rate_limiter: Arc::default(),
             registry_entries: Vec::new(),
             cost_guard: None,
+            review_queue: None,
+            routine_engine: None,
+            webhooks: None,
+            git_mirror: None,
+            events: crate::events::EventBus::new(),
             startup_time: std::time::Instant::now(),
             legal_config: None,
             runtime_facts: crate::compliance::ComplianceRuntimeFacts::default(),
+            review_token_key: crate::channels::web::auth::derive_review_token_key("test-token"),
         }
     }
 }
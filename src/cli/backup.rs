@@ -11,6 +11,7 @@ use crate::legal::backup::{
     export_matter_retrieval_packet, reencrypt_matter_files, scan_matter_encryption,
     verify_backup_file,
 };
+use crate::legal::backup_schedule::{backup_now, list_backups};
 use crate::workspace::{LegalContentPolicy, Workspace};
 
 #[derive(Subcommand, Debug, Clone)]
@@ -25,6 +26,16 @@ pub enum BackupCommand {
         include_ai_packets: bool,
     },
 
+    /// Create a backup in ~/.clawyer/backups now and apply retention rotation.
+    Now {
+        /// Include AI packet previews in snapshot metadata.
+        #[arg(long, default_value_t = false)]
+        include_ai_packets: bool,
+    },
+
+    /// List backups in ~/.clawyer/backups, newest first.
+    List,
+
     /// Verify backup integrity/decryptability and checksum consistency.
     Verify {
         /// Input backup file path.
@@ -80,6 +91,10 @@ pub enum BackupCommand {
 }
 
 pub async fn run_backup_command(cmd: BackupCommand) -> anyhow::Result<()> {
+    if matches!(cmd, BackupCommand::List) {
+        return list_backups_command().await;
+    }
+
     let config = Config::from_env()
         .await
         .map_err(|e| anyhow::anyhow!("{}", e))?;
@@ -139,6 +154,40 @@ pub async fn run_backup_command(cmd: BackupCommand) -> anyhow::Result<()> {
                 }
             }
         }
+        BackupCommand::Now { include_ai_packets } => {
+            let result = backup_now(
+                db.as_ref(),
+                &workspace,
+                user_id,
+                &master_key,
+                &BackupCreateOptions {
+                    include_ai_packets: include_ai_packets || config.backup.include_ai_packets,
+                    matter_root: config.legal.matter_root.clone(),
+                },
+                config.backup.retention_count,
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+            println!("Backup created:");
+            println!("  id: {}", result.created.artifact.id);
+            println!("  path: {}", result.created.artifact.path);
+            println!("  size: {} bytes", result.created.artifact.size_bytes);
+            if !result.rotated.is_empty() {
+                println!(
+                    "Rotated (retention {}): {}",
+                    config.backup.retention_count,
+                    result.rotated.join(", ")
+                );
+            }
+            if !result.created.warnings.is_empty() {
+                println!("Warnings:");
+                for warning in result.created.warnings {
+                    println!("  - {}", warning);
+                }
+            }
+        }
+        BackupCommand::List => list_backups_command().await?,
         BackupCommand::Verify { input } => {
             let report = verify_backup_file(&input, &master_key)
                 .await
@@ -328,3 +377,22 @@ pub async fn run_backup_command(cmd: BackupCommand) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+async fn list_backups_command() -> anyhow::Result<()> {
+    let dir = crate::legal::backup::backups_dir().map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let listings = list_backups(&dir)
+        .await
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    if listings.is_empty() {
+        println!("No backups in {}", dir.display());
+        return Ok(());
+    }
+    println!("Backups in {}:", dir.display());
+    for listing in listings {
+        println!(
+            "  {:<56} {:<26} {:>10} bytes",
+            listing.id, listing.created_at, listing.size_bytes
+        );
+    }
+    Ok(())
+}
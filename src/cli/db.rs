@@ -0,0 +1,254 @@
+//! Database maintenance CLI commands.
+
+use std::path::PathBuf;
+
+use clap::Subcommand;
+use secrecy::SecretString;
+
+use crate::config::{DatabaseBackend, DatabaseConfig};
+use crate::db::migrate::{count_entities, migrate_backend};
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum DbCommand {
+    /// Copy matters, clients, billing, workspace files, settings, and routines
+    /// between the libsql and postgres backends, then verify row counts
+    MigrateBackend {
+        /// Backend to copy from ("libsql" or "postgres")
+        #[arg(long)]
+        from: DatabaseBackend,
+
+        /// Backend to copy into ("libsql" or "postgres")
+        #[arg(long)]
+        to: DatabaseBackend,
+
+        /// PostgreSQL URL (default: DATABASE_URL)
+        #[arg(long)]
+        postgres_url: Option<String>,
+
+        /// libSQL database file (default: LIBSQL_PATH or ~/.clawyer/clawyer.db)
+        #[arg(long)]
+        libsql_path: Option<PathBuf>,
+
+        /// Owner user whose data is copied
+        #[arg(long, default_value = "default")]
+        user: String,
+
+        /// Only count source rows; write nothing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Copy even if the target already has matters for this user
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Run a database maintenance command.
+pub async fn run_db_command(cmd: DbCommand) -> anyhow::Result<()> {
+    match cmd {
+        DbCommand::MigrateBackend {
+            from,
+            to,
+            postgres_url,
+            libsql_path,
+            user,
+            dry_run,
+            force,
+        } => {
+            migrate_backend_command(from, to, postgres_url, libsql_path, &user, dry_run, force)
+                .await
+        }
+    }
+}
+
+/// Database config for one side of the copy: the environment's settings with
+/// the backend (and its location) overridden.
+fn side_config(
+    base: &DatabaseConfig,
+    backend: DatabaseBackend,
+    postgres_url: Option<&str>,
+    libsql_path: Option<&PathBuf>,
+) -> anyhow::Result<DatabaseConfig> {
+    let mut config = base.clone();
+    config.backend = backend;
+    // The copy writes through the primary; never route reads to a replica.
+    config.read_url = None;
+    match backend {
+        DatabaseBackend::Postgres => {
+            if let Some(url) = postgres_url {
+                config.url = SecretString::from(url.to_string());
+            }
+            if !config.url().starts_with("postgres") {
+                anyhow::bail!(
+                    "a PostgreSQL URL is required: pass --postgres-url or set DATABASE_URL"
+                );
+            }
+        }
+        DatabaseBackend::LibSql => {
+            config.libsql_path = Some(
+                libsql_path
+                    .cloned()
+                    .or_else(|| base.libsql_path.clone())
+                    .unwrap_or_else(crate::config::default_libsql_path),
+            );
+            // Always copy the local file, not a Turso sync session.
+            config.libsql_url = None;
+            config.libsql_auth_token = None;
+        }
+    }
+    Ok(config)
+}
+
+async fn migrate_backend_command(
+    from: DatabaseBackend,
+    to: DatabaseBackend,
+    postgres_url: Option<String>,
+    libsql_path: Option<PathBuf>,
+    user_id: &str,
+    dry_run: bool,
+    force: bool,
+) -> anyhow::Result<()> {
+    if from == to {
+        anyhow::bail!("--from and --to must be different backends");
+    }
+    let base = DatabaseConfig::resolve_for_startup(true).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let source_config = side_config(&base, from, postgres_url.as_deref(), libsql_path.as_ref())?;
+    let target_config = side_config(&base, to, postgres_url.as_deref(), libsql_path.as_ref())?;
+
+    println!("Connecting to source ({})...", from);
+    let source = crate::db::connect_from_config(&source_config)
+        .await
+        .map_err(|e| anyhow::anyhow!("source: {}", e))?;
+
+    if dry_run {
+        let counts = count_entities(source.as_ref(), user_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        println!("Dry run: rows that would be copied for user '{}':", user_id);
+        for (name, count) in counts.rows() {
+            println!("  {:<20} {}", name, count);
+        }
+        let history = source
+            .count_history_rows(user_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        println!("Rows that would NOT be copied:");
+        for (name, count) in history.rows() {
+            println!("  {:<21} {}", name, count);
+        }
+        return Ok(());
+    }
+
+    println!("Connecting to target ({}) and applying migrations...", to);
+    let target = crate::db::connect_from_config(&target_config)
+        .await
+        .map_err(|e| anyhow::anyhow!("target: {}", e))?;
+
+    let existing = target
+        .list_matters_db(user_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    if !existing.is_empty() && !force {
+        anyhow::bail!(
+            "target already has {} matter(s) for user '{}'; re-run with --force to merge into it",
+            existing.len(),
+            user_id
+        );
+    }
+
+    println!("Copying data for user '{}':", user_id);
+    let report = migrate_backend(
+        source.as_ref(),
+        target.as_ref(),
+        user_id,
+        &mut |name, count| {
+            println!("  {:<20} {}", name, count);
+        },
+    )
+    .await
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    if !report.warnings.is_empty() {
+        println!("Warnings:");
+        for warning in &report.warnings {
+            println!("  - {}", warning);
+        }
+    }
+
+    println!("Verification (source -> target):");
+    for ((name, source), (_, target)) in report.source.rows().into_iter().zip(report.target.rows())
+    {
+        let mark = if target >= source { "ok" } else { "MISMATCH" };
+        println!("  {:<20} {:>6} -> {:<6} {}", name, source, target, mark);
+    }
+    println!("Not copied (left on the source):");
+    for (name, count) in report.not_copied.rows() {
+        println!("  {:<21} {}", name, count);
+    }
+    println!("  LLM caches and API tokens are not copied either; re-issue tokens on the target.");
+
+    if !report.verified() {
+        anyhow::bail!(
+            "verification failed for {} entity type(s): {}",
+            report.mismatches.len(),
+            report.mismatches.join("; ")
+        );
+    }
+    if report.complete() {
+        println!("Migration complete. Set DATABASE_BACKEND={} to switch.", to);
+    } else {
+        println!(
+            "Copied entities verified; {} row(s) listed above stay on the source. \
+             Set DATABASE_BACKEND={} to switch once that is acceptable.",
+            report.not_copied.total(),
+            to
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base() -> DatabaseConfig {
+        DatabaseConfig {
+            backend: DatabaseBackend::LibSql,
+            url: SecretString::from("unused://libsql".to_string()),
+            pool_size: 5,
+            read_url: Some(SecretString::from("postgres://replica/db".to_string())),
+            connect_retries: 3,
+            libsql_path: None,
+            libsql_url: Some("libsql://example.turso.io".to_string()),
+            libsql_auth_token: Some(SecretString::from("token".to_string())),
+            libsql_encrypt_content: false,
+        }
+    }
+
+    #[test]
+    fn postgres_side_requires_a_url() {
+        let err = side_config(&base(), DatabaseBackend::Postgres, None, None)
+            .expect_err("placeholder url must be rejected");
+        assert!(err.to_string().contains("--postgres-url"));
+
+        let config = side_config(
+            &base(),
+            DatabaseBackend::Postgres,
+            Some("postgres://localhost/clawyer"),
+            None,
+        )
+        .expect("explicit url");
+        assert_eq!(config.url(), "postgres://localhost/clawyer");
+        assert!(config.read_url.is_none());
+    }
+
+    #[test]
+    fn libsql_side_uses_the_local_file_only() {
+        let path = PathBuf::from("/tmp/clawyer-test.db");
+        let config = side_config(&base(), DatabaseBackend::LibSql, None, Some(&path))
+            .expect("libsql config");
+        assert_eq!(config.libsql_path.as_deref(), Some(path.as_path()));
+        assert!(config.libsql_url.is_none());
+        assert!(config.libsql_auth_token.is_none());
+    }
+}
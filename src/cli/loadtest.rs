@@ -0,0 +1,479 @@
+//! `clawyer loadtest` - synthetic traffic against a running gateway.
+//!
+//! Spawns a number of virtual users that each loop over a weighted mix of
+//! operations until the run duration elapses:
+//! - **chat**: open a thread, then send a few messages into it
+//! - **ingest**: write a synthetic document under `loadtest/<run>/`
+//! - **routine**: manually fire one of the given routines
+//!
+//! Latency is measured per HTTP request. Chat sends are accepted
+//! asynchronously by the gateway, so their latency is intake latency, not
+//! time to the agent's reply. Rate-limited responses (429) are counted
+//! separately from errors so capacity limits are visible as such.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use clap::Args;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::{Rng, SeedableRng};
+
+#[derive(Args, Debug, Clone)]
+pub struct LoadtestArgs {
+    /// Base URL of the running gateway.
+    #[arg(long, default_value = "http://127.0.0.1:3000")]
+    pub url: String,
+
+    /// Gateway bearer token.
+    #[arg(long, env = "GATEWAY_AUTH_TOKEN", hide_env_values = true)]
+    pub token: String,
+
+    /// Concurrent virtual users.
+    #[arg(long, default_value_t = 4)]
+    pub users: usize,
+
+    /// Run length in seconds. Use a long duration for soak testing.
+    #[arg(long, default_value_t = 60)]
+    pub duration: u64,
+
+    /// Print an interim report every N seconds (0 = final report only).
+    #[arg(long, default_value_t = 0)]
+    pub report_interval: u64,
+
+    /// Operation mix as `op=weight` pairs (ops: chat, ingest, routine).
+    #[arg(long, default_value = "chat=6,ingest=3,routine=1")]
+    pub mix: String,
+
+    /// Messages sent per synthetic chat session.
+    #[arg(long, default_value_t = 3)]
+    pub messages_per_session: usize,
+
+    /// Routine ID to fire for `routine` operations (repeatable). Routine
+    /// weight is ignored when none are given.
+    #[arg(long = "routine")]
+    pub routines: Vec<String>,
+
+    /// Approximate size of each synthetic document, in bytes.
+    #[arg(long, default_value_t = 4096)]
+    pub document_bytes: usize,
+
+    /// Pause between operations per virtual user, in milliseconds.
+    #[arg(long, default_value_t = 0)]
+    pub think_time_ms: u64,
+}
+
+/// Kind of synthetic operation a virtual user performs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LoadOp {
+    Chat,
+    Ingest,
+    Routine,
+}
+
+impl LoadOp {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "chat" => Some(Self::Chat),
+            "ingest" => Some(Self::Ingest),
+            "routine" => Some(Self::Routine),
+            _ => None,
+        }
+    }
+}
+
+/// Parse an `op=weight,...` mix. Zero-weight entries are dropped.
+pub fn parse_mix(raw: &str) -> anyhow::Result<Vec<(LoadOp, u32)>> {
+    let mut mix = Vec::new();
+    for part in raw.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (op, weight) = part
+            .split_once('=')
+            .with_context(|| format!("mix entry '{part}' must be op=weight"))?;
+        let op = LoadOp::parse(op.trim())
+            .with_context(|| format!("unknown mix operation '{}'", op.trim()))?;
+        let weight: u32 = weight
+            .trim()
+            .parse()
+            .with_context(|| format!("invalid weight in mix entry '{part}'"))?;
+        if mix.iter().any(|(existing, _)| *existing == op) {
+            anyhow::bail!("mix lists '{}' more than once", part);
+        }
+        if weight > 0 {
+            mix.push((op, weight));
+        }
+    }
+    if mix.is_empty() {
+        anyhow::bail!("mix must give at least one operation a non-zero weight");
+    }
+    Ok(mix)
+}
+
+/// Latency and outcome counters for one request type.
+#[derive(Debug, Default, Clone)]
+pub struct EndpointStats {
+    latencies: Vec<Duration>,
+    pub errors: u64,
+    pub rate_limited: u64,
+}
+
+impl EndpointStats {
+    pub fn record(&mut self, latency: Duration, outcome: Outcome) {
+        self.latencies.push(latency);
+        match outcome {
+            Outcome::Ok => {}
+            Outcome::RateLimited => self.rate_limited += 1,
+            Outcome::Error => self.errors += 1,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.latencies.len()
+    }
+
+    /// Nearest-rank percentile (`p` in 0-100).
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        let mut sorted = self.latencies.clone();
+        sorted.sort();
+        let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.clamp(1, sorted.len()) - 1])
+    }
+}
+
+/// Result class of one request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Ok,
+    RateLimited,
+    Error,
+}
+
+impl Outcome {
+    fn from_result(result: &Result<reqwest::Response, reqwest::Error>) -> Self {
+        match result {
+            Ok(resp) if resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                Self::RateLimited
+            }
+            Ok(resp) if resp.status().is_success() => Self::Ok,
+            _ => Self::Error,
+        }
+    }
+}
+
+type SharedStats = Arc<Mutex<BTreeMap<&'static str, EndpointStats>>>;
+
+fn record(stats: &SharedStats, endpoint: &'static str, latency: Duration, outcome: Outcome) {
+    if let Ok(mut stats) = stats.lock() {
+        stats.entry(endpoint).or_default().record(latency, outcome);
+    }
+}
+
+/// Render a latency/error table for the given elapsed run time.
+pub fn format_report(stats: &BTreeMap<&'static str, EndpointStats>, elapsed: Duration) -> String {
+    fn ms(d: Option<Duration>) -> String {
+        d.map(|d| format!("{:.1}", d.as_secs_f64() * 1000.0))
+            .unwrap_or_else(|| "-".to_string())
+    }
+    let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+    let mut out = format!(
+        "{:<16} {:>8} {:>8} {:>8} {:>8} {:>9} {:>9} {:>9} {:>9}\n",
+        "endpoint", "requests", "req/s", "errors", "429s", "p50 ms", "p95 ms", "p99 ms", "max ms"
+    );
+    let mut total = 0usize;
+    let mut total_errors = 0u64;
+    for (endpoint, s) in stats {
+        total += s.count();
+        total_errors += s.errors;
+        out.push_str(&format!(
+            "{:<16} {:>8} {:>8.2} {:>8} {:>8} {:>9} {:>9} {:>9} {:>9}\n",
+            endpoint,
+            s.count(),
+            s.count() as f64 / secs,
+            s.errors,
+            s.rate_limited,
+            ms(s.percentile(50.0)),
+            ms(s.percentile(95.0)),
+            ms(s.percentile(99.0)),
+            ms(s.percentile(100.0)),
+        ));
+    }
+    let error_rate = if total == 0 {
+        0.0
+    } else {
+        total_errors as f64 * 100.0 / total as f64
+    };
+    out.push_str(&format!(
+        "\n{} requests in {:.1}s ({:.2} req/s), {:.2}% errors\n",
+        total,
+        secs,
+        total as f64 / secs,
+        error_rate
+    ));
+    out
+}
+
+/// Shared, read-only inputs for every virtual user.
+struct RunPlan {
+    client: reqwest::Client,
+    base_url: String,
+    token: String,
+    run_id: String,
+    ops: Vec<LoadOp>,
+    weights: WeightedIndex<u32>,
+    messages_per_session: usize,
+    routines: Vec<String>,
+    document_bytes: usize,
+    think_time: Duration,
+    deadline: Instant,
+}
+
+impl RunPlan {
+    async fn timed(
+        &self,
+        stats: &SharedStats,
+        endpoint: &'static str,
+        request: reqwest::RequestBuilder,
+    ) -> Option<reqwest::Response> {
+        let started = Instant::now();
+        let result = request.bearer_auth(&self.token).send().await;
+        let outcome = Outcome::from_result(&result);
+        record(stats, endpoint, started.elapsed(), outcome);
+        match (outcome, result) {
+            (Outcome::Ok, Ok(resp)) => Some(resp),
+            _ => None,
+        }
+    }
+
+    async fn chat_session(&self, stats: &SharedStats, user: usize, rng: &mut impl Rng) {
+        let thread = self
+            .timed(
+                stats,
+                "chat.new_thread",
+                self.client
+                    .post(format!("{}/api/chat/thread/new", self.base_url)),
+            )
+            .await;
+        let Some(thread) = thread else {
+            return;
+        };
+        let thread_id = thread
+            .json::<serde_json::Value>()
+            .await
+            .ok()
+            .and_then(|v| v.get("id").and_then(|id| id.as_str()).map(str::to_string));
+        for turn in 0..self.messages_per_session {
+            if Instant::now() >= self.deadline {
+                break;
+            }
+            let prompt = SYNTHETIC_PROMPTS[rng.gen_range(0..SYNTHETIC_PROMPTS.len())];
+            self.timed(
+                stats,
+                "chat.send",
+                self.client
+                    .post(format!("{}/api/chat/send", self.base_url))
+                    .json(&serde_json::json!({
+                        "content": format!("[loadtest {} u{user} t{turn}] {prompt}", self.run_id),
+                        "thread_id": thread_id,
+                    })),
+            )
+            .await;
+        }
+    }
+
+    async fn ingest_document(&self, stats: &SharedStats, user: usize, seq: u64) {
+        let path = format!("loadtest/{}/user-{user}/doc-{seq}.md", self.run_id);
+        self.timed(
+            stats,
+            "memory.write",
+            self.client
+                .post(format!("{}/api/memory/write", self.base_url))
+                .json(&serde_json::json!({
+                    "path": path,
+                    "content": synthetic_document(seq, self.document_bytes),
+                })),
+        )
+        .await;
+    }
+
+    async fn fire_routine(&self, stats: &SharedStats, rng: &mut impl Rng) {
+        let id = &self.routines[rng.gen_range(0..self.routines.len())];
+        self.timed(
+            stats,
+            "routine.trigger",
+            self.client
+                .post(format!("{}/api/routines/{}/trigger", self.base_url, id)),
+        )
+        .await;
+    }
+
+    async fn virtual_user(self: Arc<Self>, stats: SharedStats, user: usize) {
+        let mut rng = rand::rngs::StdRng::from_entropy();
+        let mut seq = 0u64;
+        while Instant::now() < self.deadline {
+            match self.ops[self.weights.sample(&mut rng)] {
+                LoadOp::Chat => self.chat_session(&stats, user, &mut rng).await,
+                LoadOp::Ingest => self.ingest_document(&stats, user, seq).await,
+                LoadOp::Routine => self.fire_routine(&stats, &mut rng).await,
+            }
+            seq += 1;
+            if !self.think_time.is_zero() {
+                tokio::time::sleep(self.think_time).await;
+            }
+        }
+    }
+}
+
+const SYNTHETIC_PROMPTS: &[&str] = &[
+    "Summarize the key deadlines on this matter.",
+    "Draft a short status update for the client.",
+    "What open tasks are assigned to me this week?",
+    "List the parties and their counsel.",
+    "Outline the next steps for discovery.",
+];
+
+fn synthetic_document(seq: u64, target_bytes: usize) -> String {
+    const PARAGRAPH: &str = "The parties acknowledge that this synthetic record was generated \
+        for capacity testing and contains no client information. ";
+    let mut doc = format!("# Load test document {seq}\n\n");
+    while doc.len() < target_bytes {
+        doc.push_str(PARAGRAPH);
+    }
+    doc
+}
+
+pub async fn run_loadtest_command(args: LoadtestArgs) -> anyhow::Result<()> {
+    let mut mix = parse_mix(&args.mix)?;
+    if args.routines.is_empty() {
+        mix.retain(|(op, _)| *op != LoadOp::Routine);
+        if mix.is_empty() {
+            anyhow::bail!("mix only contains 'routine' but no --routine IDs were given");
+        }
+    }
+    if args.users == 0 {
+        anyhow::bail!("--users must be at least 1");
+    }
+    let weights =
+        WeightedIndex::new(mix.iter().map(|(_, w)| *w)).context("invalid operation weights")?;
+    let base_url = args.url.trim_end_matches('/').to_string();
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .context("failed to build HTTP client")?;
+
+    let health = client
+        .get(format!("{base_url}/api/health"))
+        .send()
+        .await
+        .with_context(|| format!("gateway at {base_url} is not reachable"))?;
+    if !health.status().is_success() {
+        anyhow::bail!("gateway health check returned {}", health.status());
+    }
+
+    let run_id = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let duration = Duration::from_secs(args.duration);
+    println!(
+        "Load test {run_id}: {} users for {}s against {base_url} (mix: {})",
+        args.users, args.duration, args.mix
+    );
+    if mix.iter().any(|(op, _)| *op == LoadOp::Ingest) {
+        println!("Synthetic documents are written under loadtest/{run_id}/");
+    }
+
+    let started = Instant::now();
+    let plan = Arc::new(RunPlan {
+        client,
+        base_url,
+        token: args.token,
+        run_id,
+        ops: mix.iter().map(|(op, _)| *op).collect(),
+        weights,
+        messages_per_session: args.messages_per_session.max(1),
+        routines: args.routines,
+        document_bytes: args.document_bytes,
+        think_time: Duration::from_millis(args.think_time_ms),
+        deadline: started + duration,
+    });
+    let stats: SharedStats = Arc::default();
+
+    let mut users = tokio::task::JoinSet::new();
+    for user in 0..args.users {
+        users.spawn(Arc::clone(&plan).virtual_user(Arc::clone(&stats), user));
+    }
+
+    if args.report_interval > 0 {
+        let mut ticker = tokio::time::interval(Duration::from_secs(args.report_interval));
+        ticker.tick().await;
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {
+                    let snapshot = stats.lock().map(|s| s.clone()).unwrap_or_default();
+                    println!("\n-- interim ({:.0}s) --", started.elapsed().as_secs_f64());
+                    print!("{}", format_report(&snapshot, started.elapsed()));
+                }
+                joined = users.join_next() => {
+                    if joined.is_none() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    while users.join_next().await.is_some() {}
+
+    let snapshot = stats.lock().map(|s| s.clone()).unwrap_or_default();
+    println!("\n== final ==");
+    print!("{}", format_report(&snapshot, started.elapsed()));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mix_parses_weights_and_drops_zeroes() {
+        let mix = parse_mix("chat=5, ingest=0,routine=2").unwrap();
+        assert_eq!(mix, vec![(LoadOp::Chat, 5), (LoadOp::Routine, 2)]);
+        assert!(parse_mix("chat=0").is_err());
+        assert!(parse_mix("browse=1").is_err());
+        assert!(parse_mix("chat=1,chat=2").is_err());
+        assert!(parse_mix("chat").is_err());
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let mut stats = EndpointStats::default();
+        for ms in 1..=100 {
+            stats.record(Duration::from_millis(ms), Outcome::Ok);
+        }
+        stats.record(Duration::from_millis(500), Outcome::RateLimited);
+        stats.record(Duration::from_millis(900), Outcome::Error);
+        assert_eq!(stats.count(), 102);
+        assert_eq!(stats.percentile(50.0), Some(Duration::from_millis(51)));
+        assert_eq!(stats.percentile(100.0), Some(Duration::from_millis(900)));
+        assert_eq!((stats.errors, stats.rate_limited), (1, 1));
+        assert_eq!(EndpointStats::default().percentile(50.0), None);
+    }
+
+    #[test]
+    fn report_includes_totals_and_error_rate() {
+        let mut stats = BTreeMap::new();
+        let mut chat = EndpointStats::default();
+        chat.record(Duration::from_millis(10), Outcome::Ok);
+        chat.record(Duration::from_millis(30), Outcome::Error);
+        stats.insert("chat.send", chat);
+        let report = format_report(&stats, Duration::from_secs(2));
+        assert!(report.contains("chat.send"));
+        assert!(report.contains("2 requests in 2.0s (1.00 req/s), 50.00% errors"));
+    }
+
+    #[test]
+    fn synthetic_documents_reach_target_size() {
+        let doc = synthetic_document(7, 1000);
+        assert!(doc.starts_with("# Load test document 7"));
+        assert!(doc.len() >= 1000);
+    }
+}
@@ -25,8 +25,22 @@ pub enum McpCommand {
         /// Server name (e.g., "notion", "github")
         name: String,
 
-        /// Server URL (e.g., "https://mcp.notion.com")
-        url: String,
+        /// Server URL (e.g., "https://mcp.notion.com"). Omit when using --command.
+        #[arg(required_unless_present = "command")]
+        url: Option<String>,
+
+        /// Spawn a local stdio server with this command instead of using a URL
+        /// (e.g., "npx")
+        #[arg(long, conflicts_with_all = ["url", "client_id"])]
+        command: Option<String>,
+
+        /// Argument for --command (repeatable, e.g. --arg=-y --arg @scope/server)
+        #[arg(long = "arg", requires = "command", allow_hyphen_values = true)]
+        args: Vec<String>,
+
+        /// Environment variable for --command as KEY=VALUE (repeatable)
+        #[arg(long = "env", requires = "command", value_parser = parse_env_var)]
+        env: Vec<(String, String)>,
 
         /// OAuth client ID (if authentication is required)
         #[arg(long)]
@@ -103,23 +117,29 @@ pub async fn run_mcp_command(cmd: McpCommand) -> anyhow::Result<()> {
         McpCommand::Add {
             name,
             url,
+            command,
+            args,
+            env,
             client_id,
             auth_url,
             token_url,
             scopes,
             description,
-        } => {
-            add_server(
-                name,
-                url,
-                client_id,
-                auth_url,
-                token_url,
-                scopes,
-                description,
-            )
-            .await
-        }
+        } => match command {
+            Some(command) => add_stdio_server(name, command, args, env, description).await,
+            None => {
+                add_server(
+                    name,
+                    url.unwrap_or_default(),
+                    client_id,
+                    auth_url,
+                    token_url,
+                    scopes,
+                    description,
+                )
+                .await
+            }
+        },
         McpCommand::Remove { name } => remove_server(name).await,
         McpCommand::List { verbose } => list_servers(verbose).await,
         McpCommand::Auth { name, user } => auth_server(name, user).await,
@@ -193,6 +213,55 @@ async fn add_server(
     Ok(())
 }
 
+/// Parse a `KEY=VALUE` environment variable argument.
+fn parse_env_var(raw: &str) -> Result<(String, String), String> {
+    match raw.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VALUE, got '{raw}'")),
+    }
+}
+
+/// Add a local MCP server that is spawned as a child process.
+async fn add_stdio_server(
+    name: String,
+    command: String,
+    args: Vec<String>,
+    env: Vec<(String, String)>,
+    description: Option<String>,
+) -> anyhow::Result<()> {
+    let mut config =
+        McpServerConfig::new_stdio(&name, &command, args).with_env(env.into_iter().collect());
+    if let Some(desc) = description {
+        config = config.with_description(desc);
+    }
+    config.validate()?;
+
+    let db = connect_db().await;
+    let mut servers = load_servers(db.as_deref()).await?;
+    servers.upsert(config.clone());
+    save_servers(db.as_deref(), &servers).await?;
+
+    println!();
+    println!("  ✓ Added MCP server '{}'", name);
+    println!("    Command: {}", stdio_command_line(&config));
+    println!();
+    println!(
+        "  Run 'clawyer mcp test {}' to start it and list its tools.",
+        name
+    );
+    println!();
+
+    Ok(())
+}
+
+/// Display form of a stdio server's command and arguments.
+fn stdio_command_line(server: &McpServerConfig) -> String {
+    std::iter::once(server.command.as_deref().unwrap_or_default())
+        .chain(server.args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Remove an MCP server.
 async fn remove_server(name: String) -> anyhow::Result<()> {
     let db = connect_db().await;
@@ -220,6 +289,7 @@ async fn list_servers(verbose: bool) -> anyhow::Result<()> {
         println!();
         println!("  Add a server with:");
         println!("    clawyer mcp add <name> <url> [--client-id <id>]");
+        println!("    clawyer mcp add <name> --command <cmd> [--arg <arg>...]");
         println!();
         return Ok(());
     }
@@ -236,9 +306,24 @@ async fn list_servers(verbose: bool) -> anyhow::Result<()> {
             ""
         };
 
+        let target = if server.is_stdio() {
+            format!("stdio: {}", stdio_command_line(server))
+        } else {
+            server.url.clone()
+        };
+
         if verbose {
             println!("  {} {}{}", status, server.name, auth_status);
-            println!("      URL: {}", server.url);
+            if server.is_stdio() {
+                println!("      Command: {}", stdio_command_line(server));
+                if !server.env.is_empty() {
+                    let mut keys: Vec<&str> = server.env.keys().map(String::as_str).collect();
+                    keys.sort_unstable();
+                    println!("      Env: {}", keys.join(", "));
+                }
+            } else {
+                println!("      URL: {}", server.url);
+            }
             if let Some(ref desc) = server.description {
                 println!("      Description: {}", desc);
             }
@@ -250,10 +335,7 @@ async fn list_servers(verbose: bool) -> anyhow::Result<()> {
             }
             println!();
         } else {
-            println!(
-                "  {} {} - {}{}",
-                status, server.name, server.url, auth_status
-            );
+            println!("  {} {} - {}{}", status, server.name, target, auth_status);
         }
     }
 
@@ -360,7 +442,16 @@ async fn test_server(name: String, user_id: String) -> anyhow::Result<()> {
     let secrets = get_secrets_store().await?;
     let has_tokens = is_authenticated(&server, &secrets, &user_id).await;
 
-    let client = if has_tokens {
+    let client = if server.is_stdio() {
+        match McpClient::connect_stdio(server.clone()).await {
+            Ok(client) => client,
+            Err(e) => {
+                println!("  ✗ Failed to start server: {}", e);
+                println!();
+                return Ok(());
+            }
+        }
+    } else if has_tokens {
         // We have stored tokens, use authenticated client
         McpClient::new_authenticated(server.clone(), session_manager, secrets, user_id)
     } else if server.requires_auth() {
@@ -577,4 +668,47 @@ mod tests {
 
         TestCli::command().debug_assert();
     }
+
+    #[test]
+    fn test_mcp_add_stdio_parsing() {
+        use clap::Parser;
+
+        #[derive(clap::Parser)]
+        struct TestCli {
+            #[command(subcommand)]
+            cmd: McpCommand,
+        }
+
+        let cli = TestCli::parse_from([
+            "test",
+            "add",
+            "files",
+            "--command",
+            "npx",
+            "--arg",
+            "-y",
+            "--arg",
+            "@modelcontextprotocol/server-filesystem",
+            "--env",
+            "ROOT=/srv/matters",
+        ]);
+        match cli.cmd {
+            McpCommand::Add {
+                url,
+                command,
+                args,
+                env,
+                ..
+            } => {
+                assert!(url.is_none());
+                assert_eq!(command.as_deref(), Some("npx"));
+                assert_eq!(args, vec!["-y", "@modelcontextprotocol/server-filesystem"]);
+                assert_eq!(env, vec![("ROOT".to_string(), "/srv/matters".to_string())]);
+            }
+            other => panic!("expected add, got {other:?}"),
+        }
+
+        assert!(TestCli::try_parse_from(["test", "add", "files"]).is_err());
+        assert!(parse_env_var("NOVALUE").is_err());
+    }
 }
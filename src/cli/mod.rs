@@ -4,18 +4,22 @@
 //! - Running the agent (`run`)
 //! - Interactive onboarding wizard (`onboard`)
 //! - Managing configuration (`config list`, `config get`, `config set`)
-//! - Backup and restore (`backup create`, `backup verify`, `backup restore`)
+//! - Backup and restore (`backup create`, `backup now`, `backup list`, `backup verify`, `backup restore`)
+//! - Copying data between database backends (`db migrate-backend`)
 //! - Managing WASM tools (`tool install`, `tool list`, `tool remove`)
 //! - Managing MCP servers (`mcp add`, `mcp auth`, `mcp list`, `mcp test`)
 //! - Querying workspace memory (`memory search`, `memory read`, `memory write`)
 //! - Managing OS service (`service install`, `service start`, `service stop`)
 //! - Active health diagnostics (`doctor`)
+//! - Synthetic load against a running gateway (`loadtest`)
 //! - Checking system health (`status`)
 
 mod backup;
 mod completion;
 mod config;
+mod db;
 mod doctor;
+mod loadtest;
 mod mcp;
 pub mod memory;
 pub mod oauth_defaults;
@@ -28,7 +32,9 @@ mod tool;
 pub use backup::{BackupCommand, run_backup_command};
 pub use completion::Completion;
 pub use config::{ConfigCommand, run_config_command};
+pub use db::{DbCommand, run_db_command};
 pub use doctor::run_doctor_command;
+pub use loadtest::{LoadtestArgs, run_loadtest_command};
 pub use mcp::{McpCommand, run_mcp_command};
 pub use memory::MemoryCommand;
 #[cfg(feature = "postgres")]
@@ -48,6 +54,15 @@ pub enum LegalProfileArg {
     MaxLockdown,
 }
 
+/// Transport for `--mcp-serve`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum McpTransportArg {
+    /// Newline-delimited JSON-RPC on stdin/stdout (Claude Desktop).
+    Stdio,
+    /// HTTP+SSE on `--mcp-listen`, authenticated with `--mcp-token`.
+    Sse,
+}
+
 #[derive(Parser, Debug)]
 #[command(name = "clawyer")]
 #[command(about = "Secure legal AI assistant with matter-scoped workflows and hardening controls")]
@@ -99,6 +114,25 @@ pub struct Cli {
     /// Add an allowed outbound domain in legal deny-by-default mode.
     #[arg(long = "allow-domain", global = true)]
     pub allow_domain: Vec<String>,
+
+    /// Serve tools, workspace, and matter context over MCP instead of
+    /// starting the normal channels (default transport: stdio).
+    #[arg(
+        long,
+        value_enum,
+        num_args = 0..=1,
+        default_missing_value = "stdio",
+        global = true
+    )]
+    pub mcp_serve: Option<McpTransportArg>,
+
+    /// Listen address for `--mcp-serve sse`.
+    #[arg(long, default_value = "127.0.0.1:8765", global = true)]
+    pub mcp_listen: std::net::SocketAddr,
+
+    /// Bearer token required by `--mcp-serve sse` (generated when unset).
+    #[arg(long, env = "MCP_SERVE_TOKEN", hide_env_values = true, global = true)]
+    pub mcp_token: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -133,6 +167,10 @@ pub enum Command {
     #[command(subcommand)]
     Backup(BackupCommand),
 
+    /// Database maintenance (copy data between libsql and postgres)
+    #[command(subcommand)]
+    Db(DbCommand),
+
     /// Manage WASM tools
     #[command(subcommand)]
     Tool(ToolCommand),
@@ -160,6 +198,10 @@ pub enum Command {
     /// Probe external dependencies and validate configuration
     Doctor,
 
+    /// Generate synthetic chat, ingestion, and routine traffic against a
+    /// running gateway and report latency and error rates
+    Loadtest(LoadtestArgs),
+
     /// Show system health and diagnostics
     Status,
 
@@ -245,6 +287,25 @@ mod tests {
         assert!(cli.no_repl);
     }
 
+    #[test]
+    fn test_parse_mcp_serve_flag() {
+        let cli = Cli::parse_from(["clawyer", "--mcp-serve"]);
+        assert_eq!(cli.mcp_serve, Some(McpTransportArg::Stdio));
+        assert_eq!(cli.mcp_listen.to_string(), "127.0.0.1:8765");
+
+        let cli = Cli::parse_from([
+            "clawyer",
+            "--mcp-serve",
+            "sse",
+            "--mcp-listen",
+            "0.0.0.0:9000",
+        ]);
+        assert_eq!(cli.mcp_serve, Some(McpTransportArg::Sse));
+        assert_eq!(cli.mcp_listen.port(), 9000);
+
+        assert!(Cli::parse_from(["clawyer", "run"]).mcp_serve.is_none());
+    }
+
     #[test]
     fn test_parse_onboard_quickstart_flag() {
         let cli = Cli::parse_from(["clawyer", "onboard", "--quickstart"]);
@@ -268,4 +329,28 @@ mod tests {
         let parsed = Cli::try_parse_from(["clawyer", "onboard", "--quickstart", "--advanced"]);
         assert!(parsed.is_err(), "expected clap arg conflict error");
     }
+
+    #[test]
+    fn test_parse_loadtest_args() {
+        let cli = Cli::parse_from([
+            "clawyer",
+            "loadtest",
+            "--token",
+            "tok",
+            "--users",
+            "40",
+            "--routine",
+            "r1",
+            "--routine",
+            "r2",
+        ]);
+        match cli.command {
+            Some(Command::Loadtest(args)) => {
+                assert_eq!(args.users, 40);
+                assert_eq!(args.routines, vec!["r1", "r2"]);
+                assert_eq!(args.url, "http://127.0.0.1:3000");
+            }
+            _ => panic!("expected loadtest command"),
+        }
+    }
 }
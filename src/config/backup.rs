@@ -0,0 +1,50 @@
+use crate::config::helpers::parse_optional_env;
+use crate::error::ConfigError;
+
+/// Scheduled encrypted backups.
+///
+/// Disabled unless `BACKUP_INTERVAL_HOURS` is set above zero.
+/// Maps to `crate::legal::backup_schedule`.
+#[derive(Debug, Clone)]
+pub struct BackupScheduleConfig {
+    /// Hours between automatic backups; 0 disables. Env: `BACKUP_INTERVAL_HOURS` (default: 0).
+    pub interval_hours: u64,
+    /// Newest backups kept in `~/.clawyer/backups`; older ones are deleted
+    /// after each backup. 0 keeps everything. Env: `BACKUP_RETENTION_COUNT` (default: 14).
+    pub retention_count: usize,
+    /// Include AI packet previews in scheduled backups. Env: `BACKUP_INCLUDE_AI_PACKETS`.
+    pub include_ai_packets: bool,
+}
+
+impl Default for BackupScheduleConfig {
+    fn default() -> Self {
+        Self {
+            interval_hours: 0,
+            retention_count: 14,
+            include_ai_packets: false,
+        }
+    }
+}
+
+impl BackupScheduleConfig {
+    pub(crate) fn resolve() -> Result<Self, ConfigError> {
+        let defaults = Self::default();
+        Ok(Self {
+            interval_hours: parse_optional_env("BACKUP_INTERVAL_HOURS", defaults.interval_hours)?,
+            retention_count: parse_optional_env(
+                "BACKUP_RETENTION_COUNT",
+                defaults.retention_count,
+            )?,
+            include_ai_packets: parse_optional_env(
+                "BACKUP_INCLUDE_AI_PACKETS",
+                defaults.include_ai_packets,
+            )?,
+        })
+    }
+
+    /// Interval between scheduled backups, or `None` when disabled.
+    pub fn interval(&self) -> Option<std::time::Duration> {
+        (self.interval_hours > 0)
+            .then(|| std::time::Duration::from_secs(self.interval_hours.saturating_mul(3600)))
+    }
+}
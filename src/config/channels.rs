@@ -1,7 +1,9 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use secrecy::SecretString;
 
+use crate::channels::web::rate_limit::{RateLimit, RateLimitConfig};
 use crate::config::helpers::{optional_env, parse_bool_env, parse_optional_env};
 use crate::error::ConfigError;
 use crate::settings::Settings;
@@ -13,6 +15,7 @@ pub struct ChannelsConfig {
     pub http: Option<HttpConfig>,
     pub gateway: Option<GatewayConfig>,
     pub signal: Option<SignalConfig>,
+    pub sms: Option<SmsConfig>,
     /// Directory containing WASM channel modules (default: ~/.clawyer/channels/).
     pub wasm_channels_dir: std::path::PathBuf,
     /// Whether WASM channels are enabled.
@@ -42,6 +45,12 @@ pub struct GatewayConfig {
     /// Bearer token for authentication. Random hex generated at startup if unset.
     pub auth_token: Option<String>,
     pub user_id: String,
+    /// Messages buffered for the agent loop before new ones get 429.
+    pub intake_capacity: usize,
+    /// Messages one user may have queued before their new ones get 429.
+    pub intake_per_user_limit: usize,
+    /// Per-user request limits for each route group.
+    pub rate_limits: RateLimitConfig,
 }
 
 /// Signal channel configuration (signal-cli daemon HTTP/JSON-RPC).
@@ -85,6 +94,28 @@ pub struct SignalConfig {
     pub ignore_attachments: bool,
     /// Skip story messages.
     pub ignore_stories: bool,
+    /// Group ID → matter ID. Messages in a mapped group, and replies to
+    /// them, are appended to that matter's communications log.
+    pub matter_groups: HashMap<String, String>,
+}
+
+/// SMS channel configuration (Twilio Programmable Messaging).
+#[derive(Debug, Clone)]
+pub struct SmsConfig {
+    /// Twilio account SID (`AC...`).
+    pub account_sid: String,
+    /// Twilio auth token. Authenticates API calls and signs webhooks.
+    pub auth_token: SecretString,
+    /// Twilio phone number messages are sent from (E.164).
+    pub from_number: String,
+    /// Public URL Twilio posts inbound messages to (ending in `/sms/twilio`).
+    /// Webhook signatures are computed over this exact URL.
+    pub webhook_url: String,
+    /// User whose clients and consent records SMS numbers belong to.
+    pub owner_id: String,
+    /// Extra numbers (E.164, or `*`) allowed to message the agent. Numbers
+    /// matching a client's phone are always allowed.
+    pub allow_from: Vec<String>,
 }
 
 impl ChannelsConfig {
@@ -107,6 +138,15 @@ impl ChannelsConfig {
                 port: parse_optional_env("GATEWAY_PORT", 3000)?,
                 auth_token: optional_env("GATEWAY_AUTH_TOKEN")?,
                 user_id: optional_env("GATEWAY_USER_ID")?.unwrap_or_else(|| "default".to_string()),
+                intake_capacity: parse_optional_env(
+                    "GATEWAY_INTAKE_CAPACITY",
+                    crate::channels::DEFAULT_INTAKE_CAPACITY,
+                )?,
+                intake_per_user_limit: parse_optional_env(
+                    "GATEWAY_INTAKE_PER_USER_LIMIT",
+                    crate::channels::DEFAULT_PER_USER_IN_FLIGHT,
+                )?,
+                rate_limits: resolve_gateway_rate_limits(settings)?,
             })
         } else {
             None
@@ -159,6 +199,33 @@ impl ChannelsConfig {
                 ignore_stories: optional_env("SIGNAL_IGNORE_STORIES")?
                     .map(|s| s.to_lowercase() == "true" || s == "1")
                     .unwrap_or(true),
+                matter_groups: parse_signal_matter_groups(optional_env("SIGNAL_MATTER_GROUPS")?)?,
+            })
+        } else {
+            None
+        };
+
+        let sms = if let Some(account_sid) = optional_env("TWILIO_ACCOUNT_SID")? {
+            let required = |key: &str| -> Result<String, ConfigError> {
+                optional_env(key)?.ok_or_else(|| ConfigError::MissingRequired {
+                    key: key.to_string(),
+                    hint: "Required when TWILIO_ACCOUNT_SID is set".to_string(),
+                })
+            };
+            Some(SmsConfig {
+                account_sid,
+                auth_token: SecretString::from(required("TWILIO_AUTH_TOKEN")?),
+                from_number: required("TWILIO_FROM_NUMBER")?,
+                webhook_url: required("TWILIO_WEBHOOK_URL")?,
+                owner_id: optional_env("SMS_OWNER_ID")?.unwrap_or_else(|| "default".to_string()),
+                allow_from: optional_env("SMS_ALLOW_FROM")?
+                    .map(|s| {
+                        s.split(',')
+                            .map(|e| e.trim().to_string())
+                            .filter(|s| !s.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
             })
         } else {
             None
@@ -175,6 +242,7 @@ impl ChannelsConfig {
             http,
             gateway,
             signal,
+            sms,
             wasm_channels_dir: optional_env("WASM_CHANNELS_DIR")?
                 .map(PathBuf::from)
                 .unwrap_or_else(default_channels_dir),
@@ -191,6 +259,44 @@ impl ChannelsConfig {
     }
 }
 
+/// Resolve gateway rate limits from
+/// `GATEWAY_RATE_LIMIT_{CHAT,UPLOADS,API,SHARES}`, then settings, then
+/// defaults. Values are `<requests>/<window_secs>` or `off`.
+fn resolve_gateway_rate_limits(settings: &Settings) -> Result<RateLimitConfig, ConfigError> {
+    let defaults = RateLimitConfig::default();
+    let resolve = |key: &str, setting: &Option<String>, default: RateLimit| match optional_env(key)?
+        .or_else(|| setting.clone())
+    {
+        Some(raw) => RateLimit::parse(&raw).map_err(|message| ConfigError::InvalidValue {
+            key: key.to_string(),
+            message,
+        }),
+        None => Ok(default),
+    };
+    Ok(RateLimitConfig {
+        chat: resolve(
+            "GATEWAY_RATE_LIMIT_CHAT",
+            &settings.channels.gateway_rate_limit_chat,
+            defaults.chat,
+        )?,
+        uploads: resolve(
+            "GATEWAY_RATE_LIMIT_UPLOADS",
+            &settings.channels.gateway_rate_limit_uploads,
+            defaults.uploads,
+        )?,
+        api: resolve(
+            "GATEWAY_RATE_LIMIT_API",
+            &settings.channels.gateway_rate_limit_api,
+            defaults.api,
+        )?,
+        shares: resolve(
+            "GATEWAY_RATE_LIMIT_SHARES",
+            &settings.channels.gateway_rate_limit_shares,
+            defaults.shares,
+        )?,
+    })
+}
+
 /// Get the default channels directory (~/.clawyer/channels/).
 fn default_channels_dir() -> PathBuf {
     dirs::home_dir()
@@ -198,3 +304,30 @@ fn default_channels_dir() -> PathBuf {
         .join(".clawyer")
         .join("channels")
 }
+
+/// Parse `SIGNAL_MATTER_GROUPS` (`matter-id:group-id,...`) into a
+/// group ID → matter ID map. Group IDs are base64 and may end in `=`, so
+/// the matter ID comes first.
+fn parse_signal_matter_groups(raw: Option<String>) -> Result<HashMap<String, String>, ConfigError> {
+    let mut groups = HashMap::new();
+    let Some(raw) = raw else {
+        return Ok(groups);
+    };
+    for entry in raw.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let invalid = |message: String| ConfigError::InvalidValue {
+            key: "SIGNAL_MATTER_GROUPS".to_string(),
+            message,
+        };
+        let (matter, group) = entry
+            .split_once(':')
+            .ok_or_else(|| invalid(format!("expected matter-id:group-id, got '{entry}'")))?;
+        let matter_id = crate::legal::policy::sanitize_optional_matter_id(matter.trim())
+            .ok_or_else(|| invalid(format!("invalid matter ID in '{entry}'")))?;
+        let group = group.trim();
+        if group.is_empty() {
+            return Err(invalid(format!("missing group ID in '{entry}'")));
+        }
+        groups.insert(group.to_string(), matter_id);
+    }
+    Ok(groups)
+}
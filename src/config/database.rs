@@ -48,6 +48,10 @@ pub struct DatabaseConfig {
     // -- PostgreSQL fields --
     pub url: SecretString,
     pub pool_size: usize,
+    /// Optional read replica for lag-tolerant reads (search, listings, reporting).
+    pub read_url: Option<SecretString>,
+    /// Retries for transient connection errors before giving up (default: 3).
+    pub connect_retries: u32,
 
     // -- libSQL fields --
     /// Path to local libSQL database file (default: ~/.clawyer/clawyer.db).
@@ -56,6 +60,9 @@ pub struct DatabaseConfig {
     pub libsql_url: Option<String>,
     /// Turso auth token (required when libsql_url is set).
     pub libsql_auth_token: Option<SecretString>,
+    /// Encrypt workspace content and conversation messages at rest with the
+    /// secrets master key (default: false).
+    pub libsql_encrypt_content: bool,
 }
 
 impl DatabaseConfig {
@@ -98,6 +105,8 @@ impl DatabaseConfig {
             })?;
 
         let pool_size = parse_optional_env("DATABASE_POOL_SIZE", 10)?;
+        let read_url = optional_env("DATABASE_READ_URL")?.map(SecretString::from);
+        let connect_retries = parse_optional_env("DATABASE_CONNECT_RETRIES", 3)?;
 
         let libsql_path = optional_env("LIBSQL_PATH")?.map(PathBuf::from).or_else(|| {
             if backend == DatabaseBackend::LibSql {
@@ -110,6 +119,8 @@ impl DatabaseConfig {
         let libsql_url = optional_env("LIBSQL_URL")?;
         let libsql_auth_token = optional_env("LIBSQL_AUTH_TOKEN")?.map(SecretString::from);
 
+        let libsql_encrypt_content = parse_optional_env("LIBSQL_ENCRYPT_CONTENT", false)?;
+
         if libsql_url.is_some() && libsql_auth_token.is_none() {
             return Err(ConfigError::MissingRequired {
                 key: "LIBSQL_AUTH_TOKEN".to_string(),
@@ -121,9 +132,12 @@ impl DatabaseConfig {
             backend,
             url: SecretString::from(url),
             pool_size,
+            read_url,
+            connect_retries,
             libsql_path,
             libsql_url,
             libsql_auth_token,
+            libsql_encrypt_content,
         })
     }
 
@@ -131,6 +145,11 @@ impl DatabaseConfig {
     pub fn url(&self) -> &str {
         self.url.expose_secret()
     }
+
+    /// Get the read replica URL, if configured (exposes the secret).
+    pub fn read_url(&self) -> Option<&str> {
+        self.read_url.as_ref().map(|url| url.expose_secret())
+    }
 }
 
 /// Default libSQL database path (~/.clawyer/clawyer.db).
@@ -13,7 +13,7 @@ use crate::workspace::EmbeddingProvider;
 pub struct EmbeddingsConfig {
     /// Whether embeddings are enabled.
     pub enabled: bool,
-    /// Provider to use: "openai", "nearai", or "ollama"
+    /// Provider to use: "openai", "nearai", "ollama", or "local"
     pub provider: String,
     /// OpenAI API key (for OpenAI provider).
     pub openai_api_key: Option<SecretString>,
@@ -21,8 +21,19 @@ pub struct EmbeddingsConfig {
     pub model: String,
     /// Ollama base URL (for Ollama provider). Defaults to http://localhost:11434.
     pub ollama_base_url: String,
+    /// Directory with `model.onnx` + `tokenizer.json` (for the local provider).
+    /// Env: `EMBEDDING_LOCAL_MODEL_DIR`.
+    pub local_model_dir: Option<std::path::PathBuf>,
     /// Embedding vector dimension. Inferred from the model name when not set explicitly.
     pub dimension: usize,
+    /// Chunks per backfill batch. Env: `EMBEDDING_BACKFILL_BATCH_SIZE` (default: 32).
+    pub backfill_batch_size: usize,
+    /// Concurrent backfill requests. Env: `EMBEDDING_BACKFILL_CONCURRENCY` (default: 4).
+    pub backfill_concurrency: usize,
+    /// Backfill requests per minute, 0 = unlimited. Env: `EMBEDDING_BACKFILL_RPM` (default: 300).
+    pub backfill_requests_per_minute: u32,
+    /// Seconds between backlog checks. Env: `EMBEDDING_BACKFILL_IDLE_SECS` (default: 60).
+    pub backfill_idle_secs: u64,
 }
 
 impl Default for EmbeddingsConfig {
@@ -35,7 +46,12 @@ impl Default for EmbeddingsConfig {
             openai_api_key: None,
             model,
             ollama_base_url: "http://localhost:11434".to_string(),
+            local_model_dir: None,
             dimension,
+            backfill_batch_size: 32,
+            backfill_concurrency: 4,
+            backfill_requests_per_minute: 300,
+            backfill_idle_secs: 60,
         }
     }
 }
@@ -51,6 +67,9 @@ fn default_dimension_for_model(model: &str) -> usize {
         "nomic-embed-text" => 768,
         "mxbai-embed-large" => 1024,
         "all-minilm" => 384,
+        "all-MiniLM-L6-v2" | "all-MiniLM-L12-v2" | "bge-small-en-v1.5" => 384,
+        "all-mpnet-base-v2" | "bge-base-en-v1.5" | "nomic-embed-text-v1.5" => 768,
+        "bge-large-en-v1.5" => 1024,
         _ => 1536,
     }
 }
@@ -62,8 +81,22 @@ impl EmbeddingsConfig {
         let provider = optional_env("EMBEDDING_PROVIDER")?
             .unwrap_or_else(|| settings.embeddings.provider.clone());
 
-        let model =
-            optional_env("EMBEDDING_MODEL")?.unwrap_or_else(|| settings.embeddings.model.clone());
+        let local_model_dir = optional_env("EMBEDDING_LOCAL_MODEL_DIR")?
+            .or_else(|| settings.embeddings.local_model_dir.clone())
+            .map(std::path::PathBuf::from);
+
+        // A local model is named after its directory unless set explicitly,
+        // so the dimension can be inferred for common sentence-transformers.
+        let model = match optional_env("EMBEDDING_MODEL")? {
+            Some(model) => model,
+            None if provider == "local" => local_model_dir
+                .as_deref()
+                .and_then(|dir| dir.file_name())
+                .and_then(|name| name.to_str())
+                .map(str::to_string)
+                .unwrap_or_else(|| settings.embeddings.model.clone()),
+            None => settings.embeddings.model.clone(),
+        };
 
         let ollama_base_url = optional_env("OLLAMA_BASE_URL")?
             .or_else(|| settings.ollama_base_url.clone())
@@ -80,10 +113,49 @@ impl EmbeddingsConfig {
             openai_api_key,
             model,
             ollama_base_url,
+            local_model_dir,
             dimension,
+            backfill_batch_size: parse_optional_env("EMBEDDING_BACKFILL_BATCH_SIZE", 32)?,
+            backfill_concurrency: parse_optional_env("EMBEDDING_BACKFILL_CONCURRENCY", 4)?,
+            backfill_requests_per_minute: parse_optional_env("EMBEDDING_BACKFILL_RPM", 300)?,
+            backfill_idle_secs: parse_optional_env("EMBEDDING_BACKFILL_IDLE_SECS", 60)?,
         })
     }
 
+    /// Under `LLM_AIR_GAPPED`, only the in-process `local` provider or an
+    /// Ollama server on a local address may embed workspace content.
+    pub(crate) fn ensure_air_gapped(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let local = match self.provider.as_str() {
+            "local" => true,
+            "ollama" => crate::config::llm::is_local_endpoint(&self.ollama_base_url),
+            _ => false,
+        };
+        if local {
+            Ok(())
+        } else {
+            Err(ConfigError::InvalidValue {
+                key: "EMBEDDING_PROVIDER".to_string(),
+                message: format!(
+                    "LLM_AIR_GAPPED requires the 'local' or a local 'ollama' embedding provider, not '{}'",
+                    self.provider
+                ),
+            })
+        }
+    }
+
+    /// Convert to the workspace backfill worker config.
+    pub fn backfill_config(&self) -> crate::workspace::backfill::EmbeddingBackfillConfig {
+        crate::workspace::backfill::EmbeddingBackfillConfig {
+            batch_size: self.backfill_batch_size.max(1),
+            concurrency: self.backfill_concurrency.max(1),
+            requests_per_minute: self.backfill_requests_per_minute,
+            idle_interval: std::time::Duration::from_secs(self.backfill_idle_secs.max(1)),
+        }
+    }
+
     /// Get the OpenAI API key if configured.
     pub fn openai_api_key(&self) -> Option<&str> {
         self.openai_api_key.as_ref().map(|s| s.expose_secret())
@@ -128,6 +200,7 @@ impl EmbeddingsConfig {
                         .with_model(&self.model, self.dimension),
                 ))
             }
+            "local" => self.create_local_provider(),
             _ => {
                 if let Some(api_key) = self.openai_api_key() {
                     tracing::info!(
@@ -147,6 +220,37 @@ impl EmbeddingsConfig {
             }
         }
     }
+
+    #[cfg(feature = "local-embeddings")]
+    fn create_local_provider(&self) -> Option<Arc<dyn EmbeddingProvider>> {
+        let Some(dir) = self.local_model_dir.as_deref() else {
+            tracing::warn!("Local embeddings configured but EMBEDDING_LOCAL_MODEL_DIR not set");
+            return None;
+        };
+        match crate::workspace::LocalOnnxEmbeddings::from_dir(dir, self.dimension) {
+            Ok(provider) => {
+                tracing::info!(
+                    "Embeddings enabled via local ONNX model (model: {}, dir: {}, dim: {})",
+                    self.model,
+                    dir.display(),
+                    self.dimension,
+                );
+                Some(Arc::new(provider.with_model_name(&self.model)))
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load local embedding model: {}", e);
+                None
+            }
+        }
+    }
+
+    #[cfg(not(feature = "local-embeddings"))]
+    fn create_local_provider(&self) -> Option<Arc<dyn EmbeddingProvider>> {
+        tracing::warn!(
+            "Local embeddings configured but this build lacks the `local-embeddings` feature"
+        );
+        None
+    }
 }
 
 #[cfg(test)]
@@ -162,6 +266,8 @@ mod tests {
             std::env::remove_var("EMBEDDING_ENABLED");
             std::env::remove_var("EMBEDDING_PROVIDER");
             std::env::remove_var("EMBEDDING_MODEL");
+            std::env::remove_var("EMBEDDING_LOCAL_MODEL_DIR");
+            std::env::remove_var("EMBEDDING_DIMENSION");
             std::env::remove_var("OPENAI_API_KEY");
         }
     }
@@ -246,4 +352,51 @@ mod tests {
             std::env::remove_var("EMBEDDING_ENABLED");
         }
     }
+
+    #[test]
+    fn local_provider_names_model_after_directory() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_embedding_env();
+
+        let settings = Settings {
+            embeddings: EmbeddingsSettings {
+                enabled: true,
+                provider: "local".to_string(),
+                local_model_dir: Some("/opt/models/all-MiniLM-L6-v2".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let config = EmbeddingsConfig::resolve(&settings).expect("resolve should succeed");
+        assert_eq!(config.model, "all-MiniLM-L6-v2");
+        assert_eq!(config.dimension, 384);
+        assert_eq!(
+            config.local_model_dir.as_deref(),
+            Some(std::path::Path::new("/opt/models/all-MiniLM-L6-v2"))
+        );
+    }
+
+    #[test]
+    fn air_gap_allows_only_local_embedding_providers() {
+        let enabled = |provider: &str, ollama_base_url: &str| EmbeddingsConfig {
+            enabled: true,
+            provider: provider.to_string(),
+            ollama_base_url: ollama_base_url.to_string(),
+            ..Default::default()
+        };
+        assert!(enabled("local", "").ensure_air_gapped().is_ok());
+        assert!(
+            enabled("ollama", "http://localhost:11434")
+                .ensure_air_gapped()
+                .is_ok()
+        );
+        assert!(
+            enabled("ollama", "https://embed.example.com")
+                .ensure_air_gapped()
+                .is_err()
+        );
+        assert!(enabled("openai", "").ensure_air_gapped().is_err());
+        assert!(EmbeddingsConfig::default().ensure_air_gapped().is_ok());
+    }
 }
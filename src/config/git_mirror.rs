@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use crate::config::helpers::{optional_env, parse_optional_env, parse_string_env};
+use crate::error::ConfigError;
+
+/// Git mirror of the workspace.
+///
+/// Disabled unless `WORKSPACE_GIT_MIRROR_PATH` is set.
+/// Maps to `crate::workspace::git_mirror::GitMirror`.
+#[derive(Debug, Clone, Default)]
+pub struct GitMirrorConfig {
+    /// Bare repository the workspace is mirrored into. Env: `WORKSPACE_GIT_MIRROR_PATH`.
+    pub path: Option<PathBuf>,
+    /// Remote (name or URL) pushed to after each sync. Env: `WORKSPACE_GIT_MIRROR_REMOTE`.
+    pub remote: Option<String>,
+    /// Branch the mirror commits to. Env: `WORKSPACE_GIT_MIRROR_BRANCH` (default: main).
+    pub branch: String,
+    /// Seconds between syncs. Env: `WORKSPACE_GIT_MIRROR_INTERVAL_SECS` (default: 300).
+    pub interval_secs: u64,
+}
+
+impl GitMirrorConfig {
+    pub(crate) fn resolve() -> Result<Self, ConfigError> {
+        let interval_secs = parse_optional_env("WORKSPACE_GIT_MIRROR_INTERVAL_SECS", 300)?;
+        if interval_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                key: "WORKSPACE_GIT_MIRROR_INTERVAL_SECS".to_string(),
+                message: "must be at least 1".to_string(),
+            });
+        }
+        Ok(Self {
+            path: optional_env("WORKSPACE_GIT_MIRROR_PATH")?.map(PathBuf::from),
+            remote: optional_env("WORKSPACE_GIT_MIRROR_REMOTE")?,
+            branch: parse_string_env("WORKSPACE_GIT_MIRROR_BRANCH", "main")?,
+            interval_secs,
+        })
+    }
+
+    /// Build the mirror, or `None` when no repository path is configured.
+    pub fn to_mirror(&self) -> Option<crate::workspace::git_mirror::GitMirror> {
+        self.path.as_ref().map(|path| {
+            crate::workspace::git_mirror::GitMirror::new(
+                path.clone(),
+                self.remote.clone(),
+                self.branch.clone(),
+            )
+        })
+    }
+}
@@ -11,6 +11,8 @@ pub struct HygieneConfig {
     pub enabled: bool,
     /// Days before `daily/` documents are deleted. Env: `MEMORY_HYGIENE_RETENTION_DAYS` (default: 30).
     pub retention_days: u32,
+    /// Days deleted documents stay in the trash. Env: `MEMORY_TRASH_RETENTION_DAYS` (default: 30).
+    pub trash_retention_days: u32,
     /// Minimum hours between hygiene passes. Env: `MEMORY_HYGIENE_CADENCE_HOURS` (default: 12).
     pub cadence_hours: u32,
 }
@@ -20,6 +22,7 @@ impl Default for HygieneConfig {
         Self {
             enabled: true,
             retention_days: 30,
+            trash_retention_days: 30,
             cadence_hours: 12,
         }
     }
@@ -30,6 +33,7 @@ impl HygieneConfig {
         Ok(Self {
             enabled: parse_bool_env("MEMORY_HYGIENE_ENABLED", true)?,
             retention_days: parse_optional_env("MEMORY_HYGIENE_RETENTION_DAYS", 30)?,
+            trash_retention_days: parse_optional_env("MEMORY_TRASH_RETENTION_DAYS", 30)?,
             cadence_hours: parse_optional_env("MEMORY_HYGIENE_CADENCE_HOURS", 12)?,
         })
     }
@@ -40,6 +44,7 @@ impl HygieneConfig {
         crate::workspace::hygiene::HygieneConfig {
             enabled: self.enabled,
             retention_days: self.retention_days,
+            trash_retention_days: self.trash_retention_days,
             cadence_hours: self.cadence_hours,
             state_dir: dirs::home_dir()
                 .unwrap_or_else(|| std::path::PathBuf::from("."))
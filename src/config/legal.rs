@@ -1,6 +1,9 @@
 use std::path::{Component, PathBuf};
 
-use crate::config::helpers::{optional_env, parse_bool_env, parse_string_env};
+use crate::config::LlmRouteTarget;
+use crate::config::helpers::{
+    optional_env, parse_bool_env, parse_option_env, parse_optional_env, parse_string_env,
+};
 use crate::error::ConfigError;
 use crate::settings::Settings;
 
@@ -64,6 +67,33 @@ pub struct LegalEncryptionConfig {
     pub require_master_key_in_max_lockdown: bool,
 }
 
+/// Legal per-matter storage quota controls.
+#[derive(Debug, Clone, Default)]
+pub struct LegalStorageConfig {
+    /// Soft quota per matter in bytes; `None` disables quota checks.
+    pub soft_quota_bytes: Option<u64>,
+    /// Percentage of the soft quota at which warnings start.
+    pub warn_at_percent: u8,
+}
+
+/// One LLM routing rule. `None` for `task` or `confidentiality` matches any
+/// request, including untagged ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegalLlmRoute {
+    pub task: Option<crate::llm::LlmTask>,
+    /// Matter confidentiality level as written in `matter.yaml`, lower-cased.
+    pub confidentiality: Option<String>,
+    /// Models tried in order; later entries are failover targets.
+    pub models: Vec<LlmRouteTarget>,
+}
+
+/// Legal per-task / per-confidentiality LLM routing controls.
+#[derive(Debug, Clone, Default)]
+pub struct LegalLlmRoutingConfig {
+    /// Checked in order; the first matching route serves the request.
+    pub routes: Vec<LegalLlmRoute>,
+}
+
 /// Legal workflow profile and policy controls.
 #[derive(Debug, Clone)]
 pub struct LegalConfig {
@@ -78,10 +108,14 @@ pub struct LegalConfig {
     pub conflict_check_enabled: bool,
     pub conflict_file_fallback_enabled: bool,
     pub conflict_reindex_on_startup: bool,
+    /// Hold outbound messages to external channels in the review queue.
+    pub supervised_mode: bool,
     pub network: LegalNetworkConfig,
     pub audit: LegalAuditConfig,
     pub redaction: LegalRedactionConfig,
     pub encryption: LegalEncryptionConfig,
+    pub storage: LegalStorageConfig,
+    pub llm_routing: LegalLlmRoutingConfig,
 }
 
 fn parse_domains_csv(raw: &str) -> Vec<String> {
@@ -204,6 +238,72 @@ fn validate_matter_root(raw: &str) -> Result<String, ConfigError> {
         .map(|s| s.to_string())
 }
 
+fn resolve_storage(settings: &Settings) -> Result<LegalStorageConfig, ConfigError> {
+    let soft_quota_mb = parse_option_env::<u64>("LEGAL_MATTER_SOFT_QUOTA_MB")?
+        .or(settings.legal.storage.soft_quota_mb)
+        .filter(|mb| *mb > 0);
+    let warn_at_percent = parse_optional_env(
+        "LEGAL_MATTER_QUOTA_WARN_PERCENT",
+        settings.legal.storage.warn_at_percent,
+    )?;
+    if !(1..=100).contains(&warn_at_percent) {
+        return Err(ConfigError::InvalidValue {
+            key: "LEGAL_MATTER_QUOTA_WARN_PERCENT".to_string(),
+            message: "warning threshold must be between 1 and 100".to_string(),
+        });
+    }
+    Ok(LegalStorageConfig {
+        soft_quota_bytes: soft_quota_mb.map(|mb| mb.saturating_mul(1024 * 1024)),
+        warn_at_percent,
+    })
+}
+
+fn resolve_llm_routing(settings: &Settings) -> Result<LegalLlmRoutingConfig, ConfigError> {
+    let invalid = |message: String| ConfigError::InvalidValue {
+        key: "LEGAL_LLM_ROUTES".to_string(),
+        message,
+    };
+    let raw_routes = match optional_env("LEGAL_LLM_ROUTES")? {
+        Some(raw) => serde_json::from_str::<Vec<crate::settings::LegalLlmRouteSettings>>(&raw)
+            .map_err(|e| invalid(format!("must be a JSON array of routes: {e}")))?,
+        None => settings.legal.llm_routing.routes.clone(),
+    };
+
+    let mut routes = Vec::with_capacity(raw_routes.len());
+    for (index, raw) in raw_routes.into_iter().enumerate() {
+        let task = raw
+            .task
+            .as_deref()
+            .map(str::trim)
+            .filter(|task| !task.is_empty() && *task != "*")
+            .map(|task| task.parse())
+            .transpose()
+            .map_err(|e| invalid(format!("route {index}: {e}")))?;
+        let confidentiality = raw
+            .confidentiality
+            .as_deref()
+            .map(|level| level.trim().to_ascii_lowercase())
+            .filter(|level| !level.is_empty() && level != "*");
+        if raw.models.is_empty() {
+            return Err(invalid(format!(
+                "route {index}: at least one model is required"
+            )));
+        }
+        let models = raw
+            .models
+            .iter()
+            .map(|target| target.parse::<LlmRouteTarget>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| invalid(format!("route {index}: {e}")))?;
+        routes.push(LegalLlmRoute {
+            task,
+            confidentiality,
+            models,
+        });
+    }
+    Ok(LegalLlmRoutingConfig { routes })
+}
+
 impl LegalConfig {
     pub(crate) fn resolve(settings: &Settings) -> Result<Self, ConfigError> {
         let hardening_raw = parse_string_env("LEGAL_HARDENING", settings.legal.hardening.clone())?;
@@ -262,6 +362,10 @@ impl LegalConfig {
                 "LEGAL_CONFLICT_REINDEX_ON_STARTUP",
                 settings.legal.conflict_reindex_on_startup,
             )?,
+            supervised_mode: parse_bool_env(
+                "LEGAL_SUPERVISED_MODE",
+                settings.legal.supervised_mode,
+            )?,
             network: LegalNetworkConfig {
                 deny_by_default: parse_bool_env(
                     "LEGAL_NETWORK_DENY_BY_DEFAULT",
@@ -308,6 +412,8 @@ impl LegalConfig {
                     settings.legal.encryption.require_master_key_in_max_lockdown,
                 )?,
             },
+            storage: resolve_storage(settings)?,
+            llm_routing: resolve_llm_routing(settings)?,
         })
     }
 }
@@ -351,6 +457,89 @@ mod tests {
         assert!(config.encryption.matter_scope_only);
         assert!(config.encryption.exclude_from_search);
         assert!(config.encryption.require_master_key_in_max_lockdown);
+        assert_eq!(config.storage.soft_quota_bytes, None);
+        assert_eq!(config.storage.warn_at_percent, 80);
+    }
+
+    #[test]
+    fn legal_resolve_converts_storage_quota_to_bytes() {
+        let mut settings = Settings::default();
+        settings.legal.storage.soft_quota_mb = Some(2);
+        settings.legal.storage.warn_at_percent = 90;
+
+        let config = super::LegalConfig::resolve(&settings).expect("legal config");
+        assert_eq!(config.storage.soft_quota_bytes, Some(2 * 1024 * 1024));
+        assert_eq!(config.storage.warn_at_percent, 90);
+    }
+
+    #[test]
+    fn legal_resolve_rejects_out_of_range_quota_warning() {
+        let mut settings = Settings::default();
+        settings.legal.storage.warn_at_percent = 0;
+
+        let err = super::LegalConfig::resolve(&settings).expect_err("0% must be rejected");
+        let ConfigError::InvalidValue { key, .. } = err else {
+            panic!("expected InvalidValue");
+        };
+        assert_eq!(key, "LEGAL_MATTER_QUOTA_WARN_PERCENT");
+    }
+
+    #[test]
+    fn legal_resolve_parses_llm_routes_in_order() {
+        let mut settings = Settings::default();
+        settings.legal.llm_routing.routes = vec![
+            crate::settings::LegalLlmRouteSettings {
+                task: None,
+                confidentiality: Some(" Attorney-Client-Privileged ".to_string()),
+                models: vec!["ollama:llama3:70b".to_string()],
+            },
+            crate::settings::LegalLlmRouteSettings {
+                task: Some("Drafting".to_string()),
+                confidentiality: Some("*".to_string()),
+                models: vec![
+                    "anthropic:claude-sonnet-4-20250514".to_string(),
+                    "openai:gpt-4o".to_string(),
+                ],
+            },
+        ];
+
+        let config = super::LegalConfig::resolve(&settings).expect("legal config");
+        let routes = &config.llm_routing.routes;
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].task, None);
+        assert_eq!(
+            routes[0].confidentiality.as_deref(),
+            Some("attorney-client-privileged")
+        );
+        assert_eq!(routes[0].models[0].model, "llama3:70b");
+        assert_eq!(routes[1].task, Some(crate::llm::LlmTask::Drafting));
+        assert_eq!(routes[1].confidentiality, None);
+        assert_eq!(
+            routes[1].models[1].backend,
+            crate::config::LlmBackend::OpenAi
+        );
+    }
+
+    #[test]
+    fn legal_resolve_rejects_invalid_llm_routes() {
+        for (task, models) in [
+            (Some("chat"), vec!["openai:gpt-4o"]),
+            (None, vec!["gpt-4o"]),
+            (None, vec!["mystery:model"]),
+            (None, vec![]),
+        ] {
+            let mut settings = Settings::default();
+            settings.legal.llm_routing.routes = vec![crate::settings::LegalLlmRouteSettings {
+                task: task.map(str::to_string),
+                confidentiality: None,
+                models: models.iter().map(|m| m.to_string()).collect(),
+            }];
+            let err = super::LegalConfig::resolve(&settings).expect_err("route must be rejected");
+            let ConfigError::InvalidValue { key, .. } = err else {
+                panic!("expected InvalidValue");
+            };
+            assert_eq!(key, "LEGAL_LLM_ROUTES");
+        }
     }
 
     #[test]
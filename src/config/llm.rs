@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use secrecy::SecretString;
 
-use crate::config::helpers::{optional_env, parse_optional_env};
+use crate::config::helpers::{optional_env, parse_bool_env, parse_optional_env};
 use crate::error::ConfigError;
 use crate::settings::Settings;
 
@@ -75,6 +75,52 @@ pub struct AnthropicDirectConfig {
     pub model: String,
     /// Optional base URL override (e.g. for proxies like VibeProxy).
     pub base_url: Option<String>,
+    /// Mark the system prompt and conversation prefix for Anthropic prompt
+    /// caching. Env: `ANTHROPIC_PROMPT_CACHING` (default: true).
+    pub prompt_caching: bool,
+}
+
+/// Capabilities of a self-hosted model, set per deployment because local
+/// servers do not report them reliably.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalModelCapabilities {
+    /// Context window in tokens. Env: `LOCAL_LLM_CONTEXT_LENGTH` (default: 8192).
+    pub context_length: u32,
+    /// Whether the model accepts image input. Env: `LOCAL_LLM_VISION` (default: false).
+    pub vision: bool,
+    /// Whether the server supports native tool calling. When false, tools are
+    /// withheld and the model answers in plain text.
+    /// Env: `LOCAL_LLM_TOOL_CALLING` (default: true).
+    pub tool_calling: bool,
+}
+
+impl Default for LocalModelCapabilities {
+    fn default() -> Self {
+        Self {
+            context_length: 8192,
+            vision: false,
+            tool_calling: true,
+        }
+    }
+}
+
+impl LocalModelCapabilities {
+    fn resolve() -> Result<Self, ConfigError> {
+        let defaults = Self::default();
+        let context_length =
+            parse_optional_env("LOCAL_LLM_CONTEXT_LENGTH", defaults.context_length)?;
+        if context_length < 1024 {
+            return Err(ConfigError::InvalidValue {
+                key: "LOCAL_LLM_CONTEXT_LENGTH".to_string(),
+                message: "must be at least 1024 tokens".to_string(),
+            });
+        }
+        Ok(Self {
+            context_length,
+            vision: parse_bool_env("LOCAL_LLM_VISION", defaults.vision)?,
+            tool_calling: parse_bool_env("LOCAL_LLM_TOOL_CALLING", defaults.tool_calling)?,
+        })
+    }
 }
 
 /// Configuration for local Ollama.
@@ -82,6 +128,7 @@ pub struct AnthropicDirectConfig {
 pub struct OllamaConfig {
     pub base_url: String,
     pub model: String,
+    pub capabilities: LocalModelCapabilities,
 }
 
 /// Configuration for any OpenAI-compatible endpoint.
@@ -93,6 +140,9 @@ pub struct OpenAiCompatibleConfig {
     /// Extra HTTP headers injected into every LLM request.
     /// Parsed from `LLM_EXTRA_HEADERS` env var (format: `Key:Value,Key2:Value2`).
     pub extra_headers: Vec<(String, String)>,
+    /// Set when `base_url` is a local endpoint (llama.cpp, vLLM, LM Studio);
+    /// hosted gateways such as OpenRouter keep their own limits.
+    pub local_capabilities: Option<LocalModelCapabilities>,
 }
 
 /// Configuration for Tinfoil private inference.
@@ -102,6 +152,39 @@ pub struct TinfoilConfig {
     pub model: String,
 }
 
+/// A `backend:model` pair naming one model on a specific backend, e.g.
+/// `anthropic:claude-sonnet-4-20250514` or `ollama:llama3:70b`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LlmRouteTarget {
+    pub backend: LlmBackend,
+    pub model: String,
+}
+
+impl std::str::FromStr for LlmRouteTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (backend, model) = s
+            .trim()
+            .split_once(':')
+            .ok_or_else(|| format!("model target '{s}' must be 'backend:model'"))?;
+        let model = model.trim();
+        if model.is_empty() {
+            return Err(format!("model target '{s}' is missing a model name"));
+        }
+        Ok(Self {
+            backend: backend.trim().parse()?,
+            model: model.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for LlmRouteTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.backend, self.model)
+    }
+}
+
 /// LLM provider configuration.
 ///
 /// NEAR AI remains the default backend. Users can switch to other providers
@@ -122,6 +205,12 @@ pub struct LlmConfig {
     pub openai_compatible: Option<OpenAiCompatibleConfig>,
     /// Tinfoil config (populated when backend=tinfoil)
     pub tinfoil: Option<TinfoilConfig>,
+    /// Restrict inference to local endpoints. Env: `LLM_AIR_GAPPED`.
+    ///
+    /// Requires an `ollama` or `openai_compatible` backend on a loopback or
+    /// private-network address, and drops hosted backend credentials so no
+    /// route can reach them.
+    pub air_gapped: bool,
 }
 
 /// NEAR AI configuration.
@@ -156,9 +245,12 @@ pub struct NearAiConfig {
     pub circuit_breaker_threshold: Option<u32>,
     /// How long (seconds) the circuit stays open before allowing a probe (default: 30).
     pub circuit_breaker_recovery_secs: u64,
-    /// Enable in-memory response caching for `complete()` calls.
-    /// Saves tokens on repeated prompts within a session. Default: false.
+    /// Enable response caching for `complete()` calls and opted-in tool
+    /// completions. Saves tokens on repeated prompts. Default: false.
     pub response_cache_enabled: bool,
+    /// Also persist cached responses in the database so they survive
+    /// restarts and are shared across sessions (default: true).
+    pub response_cache_persistent: bool,
     /// TTL in seconds for cached responses (default: 3600 = 1 hour).
     pub response_cache_ttl_secs: u64,
     /// Max cached responses before LRU eviction (default: 1000).
@@ -232,6 +324,7 @@ impl LlmConfig {
                 })?,
             circuit_breaker_recovery_secs: parse_optional_env("CIRCUIT_BREAKER_RECOVERY_SECS", 30)?,
             response_cache_enabled: parse_optional_env("RESPONSE_CACHE_ENABLED", false)?,
+            response_cache_persistent: parse_optional_env("RESPONSE_CACHE_PERSISTENT", true)?,
             response_cache_ttl_secs: parse_optional_env("RESPONSE_CACHE_TTL_SECS", 3600)?,
             response_cache_max_entries: parse_optional_env("RESPONSE_CACHE_MAX_ENTRIES", 1000)?,
             failover_cooldown_secs: parse_optional_env("LLM_FAILOVER_COOLDOWN_SECS", 300)?,
@@ -239,14 +332,18 @@ impl LlmConfig {
             smart_routing_cascade: parse_optional_env("SMART_ROUTING_CASCADE", true)?,
         };
 
-        // Resolve provider-specific configs based on backend
-        let openai = if backend == LlmBackend::OpenAi {
-            let api_key = optional_env("OPENAI_API_KEY")?
-                .map(SecretString::from)
-                .ok_or_else(|| ConfigError::MissingRequired {
-                    key: "OPENAI_API_KEY".to_string(),
-                    hint: "Set OPENAI_API_KEY when LLM_BACKEND=openai".to_string(),
-                })?;
+        // Resolve provider-specific configs: required for the active backend,
+        // and picked up for other backends whenever their credentials are set
+        // so legal LLM routes can target them.
+        let openai_key = optional_env("OPENAI_API_KEY")?;
+        let openai = if backend == LlmBackend::OpenAi || openai_key.is_some() {
+            let api_key =
+                openai_key
+                    .map(SecretString::from)
+                    .ok_or_else(|| ConfigError::MissingRequired {
+                        key: "OPENAI_API_KEY".to_string(),
+                        hint: "Set OPENAI_API_KEY when LLM_BACKEND=openai".to_string(),
+                    })?;
             let model = optional_env("OPENAI_MODEL")?.unwrap_or_else(|| "gpt-4o".to_string());
             let base_url = optional_env("OPENAI_BASE_URL")?;
             Some(OpenAiDirectConfig {
@@ -258,13 +355,14 @@ impl LlmConfig {
             None
         };
 
-        let anthropic = if backend == LlmBackend::Anthropic {
-            let api_key = optional_env("ANTHROPIC_API_KEY")?
-                .map(SecretString::from)
-                .ok_or_else(|| ConfigError::MissingRequired {
+        let anthropic_key = optional_env("ANTHROPIC_API_KEY")?;
+        let anthropic = if backend == LlmBackend::Anthropic || anthropic_key.is_some() {
+            let api_key = anthropic_key.map(SecretString::from).ok_or_else(|| {
+                ConfigError::MissingRequired {
                     key: "ANTHROPIC_API_KEY".to_string(),
                     hint: "Set ANTHROPIC_API_KEY when LLM_BACKEND=anthropic".to_string(),
-                })?;
+                }
+            })?;
             let model = optional_env("ANTHROPIC_MODEL")?
                 .unwrap_or_else(|| "claude-sonnet-4-20250514".to_string());
             let base_url = optional_env("ANTHROPIC_BASE_URL")?;
@@ -272,60 +370,73 @@ impl LlmConfig {
                 api_key,
                 model,
                 base_url,
+                prompt_caching: parse_optional_env("ANTHROPIC_PROMPT_CACHING", true)?,
             })
         } else {
             None
         };
 
-        let ollama = if backend == LlmBackend::Ollama {
-            let base_url = optional_env("OLLAMA_BASE_URL")?
-                .or_else(|| settings.ollama_base_url.clone())
-                .unwrap_or_else(|| "http://localhost:11434".to_string());
+        let ollama_base_url =
+            optional_env("OLLAMA_BASE_URL")?.or_else(|| settings.ollama_base_url.clone());
+        let ollama = if backend == LlmBackend::Ollama || ollama_base_url.is_some() {
+            let base_url = ollama_base_url.unwrap_or_else(|| "http://localhost:11434".to_string());
             let model = optional_env("OLLAMA_MODEL")?.unwrap_or_else(|| "llama3".to_string());
-            Some(OllamaConfig { base_url, model })
-        } else {
-            None
-        };
-
-        let openai_compatible = if backend == LlmBackend::OpenAiCompatible {
-            let base_url = optional_env("LLM_BASE_URL")?
-                .or_else(|| settings.openai_compatible_base_url.clone())
-                .ok_or_else(|| ConfigError::MissingRequired {
-                    key: "LLM_BASE_URL".to_string(),
-                    hint: "Set LLM_BASE_URL when LLM_BACKEND=openai_compatible".to_string(),
-                })?;
-            let api_key = optional_env("LLM_API_KEY")?.map(SecretString::from);
-            let model = optional_env("LLM_MODEL")?
-                .or_else(|| settings.selected_model.clone())
-                .unwrap_or_else(|| "default".to_string());
-            let extra_headers = optional_env("LLM_EXTRA_HEADERS")?
-                .map(|val| parse_extra_headers(&val))
-                .transpose()?
-                .unwrap_or_default();
-            Some(OpenAiCompatibleConfig {
+            Some(OllamaConfig {
                 base_url,
-                api_key,
                 model,
-                extra_headers,
+                capabilities: LocalModelCapabilities::resolve()?,
             })
         } else {
             None
         };
 
-        let tinfoil = if backend == LlmBackend::Tinfoil {
-            let api_key = optional_env("TINFOIL_API_KEY")?
-                .map(SecretString::from)
-                .ok_or_else(|| ConfigError::MissingRequired {
+        let compatible_base_url =
+            optional_env("LLM_BASE_URL")?.or_else(|| settings.openai_compatible_base_url.clone());
+        let openai_compatible =
+            if backend == LlmBackend::OpenAiCompatible || compatible_base_url.is_some() {
+                let base_url = compatible_base_url.ok_or_else(|| ConfigError::MissingRequired {
+                    key: "LLM_BASE_URL".to_string(),
+                    hint: "Set LLM_BASE_URL when LLM_BACKEND=openai_compatible".to_string(),
+                })?;
+                let api_key = optional_env("LLM_API_KEY")?.map(SecretString::from);
+                let model = optional_env("LLM_MODEL")?
+                    .or_else(|| settings.selected_model.clone())
+                    .unwrap_or_else(|| "default".to_string());
+                let extra_headers = optional_env("LLM_EXTRA_HEADERS")?
+                    .map(|val| parse_extra_headers(&val))
+                    .transpose()?
+                    .unwrap_or_default();
+                let local_capabilities = if is_local_endpoint(&base_url) {
+                    Some(LocalModelCapabilities::resolve()?)
+                } else {
+                    None
+                };
+                Some(OpenAiCompatibleConfig {
+                    base_url,
+                    api_key,
+                    model,
+                    extra_headers,
+                    local_capabilities,
+                })
+            } else {
+                None
+            };
+
+        let tinfoil_key = optional_env("TINFOIL_API_KEY")?;
+        let tinfoil = if backend == LlmBackend::Tinfoil || tinfoil_key.is_some() {
+            let api_key = tinfoil_key.map(SecretString::from).ok_or_else(|| {
+                ConfigError::MissingRequired {
                     key: "TINFOIL_API_KEY".to_string(),
                     hint: "Set TINFOIL_API_KEY when LLM_BACKEND=tinfoil".to_string(),
-                })?;
+                }
+            })?;
             let model = optional_env("TINFOIL_MODEL")?.unwrap_or_else(|| "kimi-k2-5".to_string());
             Some(TinfoilConfig { api_key, model })
         } else {
             None
         };
 
-        Ok(Self {
+        let mut config = Self {
             backend,
             nearai,
             openai,
@@ -333,7 +444,82 @@ impl LlmConfig {
             ollama,
             openai_compatible,
             tinfoil,
-        })
+            air_gapped: parse_bool_env("LLM_AIR_GAPPED", false)?,
+        };
+        if config.air_gapped {
+            config.enforce_air_gap()?;
+        }
+        Ok(config)
+    }
+
+    /// Validate the local-only backend and drop hosted backend credentials.
+    fn enforce_air_gap(&mut self) -> Result<(), ConfigError> {
+        if !matches!(
+            self.backend,
+            LlmBackend::Ollama | LlmBackend::OpenAiCompatible
+        ) {
+            return Err(ConfigError::InvalidValue {
+                key: "LLM_BACKEND".to_string(),
+                message: format!(
+                    "LLM_AIR_GAPPED requires the ollama or openai_compatible backend, not {}",
+                    self.backend
+                ),
+            });
+        }
+        if let Some(ollama) = &self.ollama
+            && !is_local_endpoint(&ollama.base_url)
+        {
+            return Err(ConfigError::InvalidValue {
+                key: "OLLAMA_BASE_URL".to_string(),
+                message: format!("'{}' is not a local address", ollama.base_url),
+            });
+        }
+        if let Some(compatible) = &self.openai_compatible
+            && !is_local_endpoint(&compatible.base_url)
+        {
+            return Err(ConfigError::InvalidValue {
+                key: "LLM_BASE_URL".to_string(),
+                message: format!("'{}' is not a local address", compatible.base_url),
+            });
+        }
+        if self.openai.is_some() || self.anthropic.is_some() || self.tinfoil.is_some() {
+            tracing::warn!("LLM_AIR_GAPPED is set; ignoring hosted LLM backend credentials");
+        }
+        self.openai = None;
+        self.anthropic = None;
+        self.tinfoil = None;
+        Ok(())
+    }
+}
+
+/// Whether `url` points at this machine or a private network: loopback,
+/// RFC 1918 / unique-local / link-local / CGNAT addresses, `localhost`,
+/// single-label hosts (e.g. a Docker service name), and `.local`,
+/// `.internal`, `.lan` names.
+pub(crate) fn is_local_endpoint(url: &str) -> bool {
+    let Ok(parsed) = url::Url::parse(url) else {
+        return false;
+    };
+    match parsed.host() {
+        Some(url::Host::Ipv4(v4)) => {
+            let octets = v4.octets();
+            v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local()
+                || (octets[0] == 100 && (64..=127).contains(&octets[1]))
+        }
+        Some(url::Host::Ipv6(v6)) => {
+            v6.is_loopback() || v6.is_unique_local() || v6.is_unicast_link_local()
+        }
+        Some(url::Host::Domain(host)) => {
+            let host = host.trim_end_matches('.').to_ascii_lowercase();
+            host == "localhost"
+                || !host.contains('.')
+                || [".localhost", ".local", ".internal", ".lan"]
+                    .iter()
+                    .any(|suffix| host.ends_with(suffix))
+        }
+        None => false,
     }
 }
 
@@ -444,6 +630,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn secondary_backend_is_resolved_when_configured() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_openai_compatible_env();
+
+        let settings = Settings {
+            ollama_base_url: Some("http://gpu-box:11434".to_string()),
+            ..Default::default()
+        };
+        let cfg = LlmConfig::resolve(&settings).expect("resolve should succeed");
+        assert_eq!(cfg.backend, LlmBackend::NearAi);
+        let ollama = cfg.ollama.expect("ollama config should be picked up");
+        assert_eq!(ollama.base_url, "http://gpu-box:11434");
+    }
+
+    #[test]
+    fn route_targets_split_on_first_colon() {
+        let target: LlmRouteTarget = "ollama:llama3:70b".parse().expect("target");
+        assert_eq!(target.backend, LlmBackend::Ollama);
+        assert_eq!(target.model, "llama3:70b");
+        assert_eq!(target.to_string(), "ollama:llama3:70b");
+        assert!("openai:".parse::<LlmRouteTarget>().is_err());
+        assert!("gpt-4o".parse::<LlmRouteTarget>().is_err());
+    }
+
     #[test]
     fn test_extra_headers_parsed() {
         let result = parse_extra_headers("HTTP-Referer:https://myapp.com,X-Title:MyApp").unwrap();
@@ -508,4 +719,109 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn local_endpoints_are_recognized() {
+        for url in [
+            "http://localhost:11434",
+            "http://127.0.0.1:8080/v1",
+            "http://[::1]:8080/v1",
+            "http://10.0.4.2:8000/v1",
+            "http://192.168.1.20:1234/v1",
+            "http://100.101.2.3:11434",
+            "http://ollama:11434",
+            "http://gpu-box.internal:8000/v1",
+            "http://llm.lan/v1",
+        ] {
+            assert!(is_local_endpoint(url), "{url} should be local");
+        }
+        for url in [
+            "https://openrouter.ai/api/v1",
+            "https://api.openai.com/v1",
+            "http://8.8.8.8/v1",
+            "not a url",
+        ] {
+            assert!(!is_local_endpoint(url), "{url} should not be local");
+        }
+    }
+
+    #[test]
+    fn local_capabilities_apply_only_to_local_endpoints() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_openai_compatible_env();
+        // SAFETY: Under ENV_MUTEX.
+        unsafe {
+            std::env::set_var("LOCAL_LLM_CONTEXT_LENGTH", "16384");
+            std::env::set_var("LOCAL_LLM_TOOL_CALLING", "false");
+        }
+
+        let local = Settings {
+            llm_backend: Some("openai_compatible".to_string()),
+            openai_compatible_base_url: Some("http://127.0.0.1:8080/v1".to_string()),
+            ..Default::default()
+        };
+        let cfg = LlmConfig::resolve(&local).expect("resolve should succeed");
+        let caps = cfg
+            .openai_compatible
+            .and_then(|c| c.local_capabilities)
+            .expect("local server gets capabilities");
+        assert_eq!(caps.context_length, 16384);
+        assert!(!caps.vision);
+        assert!(!caps.tool_calling);
+
+        let hosted = Settings {
+            openai_compatible_base_url: Some("https://openrouter.ai/api/v1".to_string()),
+            ..local
+        };
+        let cfg = LlmConfig::resolve(&hosted).expect("resolve should succeed");
+        assert!(
+            cfg.openai_compatible
+                .expect("compatible config")
+                .local_capabilities
+                .is_none()
+        );
+
+        // SAFETY: Under ENV_MUTEX.
+        unsafe {
+            std::env::remove_var("LOCAL_LLM_CONTEXT_LENGTH");
+            std::env::remove_var("LOCAL_LLM_TOOL_CALLING");
+        }
+    }
+
+    #[test]
+    fn air_gapped_requires_local_backend_and_drops_hosted_credentials() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_openai_compatible_env();
+        // SAFETY: Under ENV_MUTEX.
+        unsafe {
+            std::env::set_var("LLM_AIR_GAPPED", "true");
+            std::env::set_var("ANTHROPIC_API_KEY", "sk-ant-test");
+        }
+
+        let err = LlmConfig::resolve(&Settings::default()).expect_err("nearai is hosted");
+        assert!(err.to_string().contains("LLM_BACKEND"), "{err}");
+
+        let remote_ollama = Settings {
+            llm_backend: Some("ollama".to_string()),
+            ollama_base_url: Some("https://ollama.example.com".to_string()),
+            ..Default::default()
+        };
+        let err = LlmConfig::resolve(&remote_ollama).expect_err("remote ollama");
+        assert!(err.to_string().contains("OLLAMA_BASE_URL"), "{err}");
+
+        let local_ollama = Settings {
+            llm_backend: Some("ollama".to_string()),
+            ..Default::default()
+        };
+        let cfg = LlmConfig::resolve(&local_ollama).expect("local ollama is allowed");
+        assert!(cfg.air_gapped);
+        assert!(cfg.ollama.is_some());
+        assert!(cfg.anthropic.is_none(), "hosted credentials are dropped");
+
+        // SAFETY: Under ENV_MUTEX.
+        unsafe {
+            std::env::remove_var("LLM_AIR_GAPPED");
+            std::env::remove_var("ANTHROPIC_API_KEY");
+        }
+    }
 }
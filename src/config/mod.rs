@@ -6,10 +6,12 @@
 //! table, or auto-detection.
 
 mod agent;
+mod backup;
 mod builder;
 mod channels;
 mod database;
 mod embeddings;
+mod git_mirror;
 mod heartbeat;
 pub(crate) mod helpers;
 mod hygiene;
@@ -20,6 +22,7 @@ mod safety;
 mod sandbox;
 mod secrets;
 mod skills;
+mod transcription;
 mod tunnel;
 mod wasm;
 
@@ -31,25 +34,30 @@ use crate::settings::Settings;
 
 // Re-export all public types so `crate::config::FooConfig` continues to work.
 pub use self::agent::AgentConfig;
+pub use self::backup::BackupScheduleConfig;
 pub use self::builder::BuilderModeConfig;
-pub use self::channels::{ChannelsConfig, CliConfig, GatewayConfig, HttpConfig, SignalConfig};
+pub use self::channels::{
+    ChannelsConfig, CliConfig, GatewayConfig, HttpConfig, SignalConfig, SmsConfig,
+};
 pub use self::database::{DatabaseBackend, DatabaseConfig, default_libsql_path};
 pub use self::embeddings::EmbeddingsConfig;
+pub use self::git_mirror::GitMirrorConfig;
 pub use self::heartbeat::HeartbeatConfig;
 pub use self::hygiene::HygieneConfig;
 pub use self::legal::{
-    LegalAuditConfig, LegalConfig, LegalEncryptionConfig, LegalHardeningProfile,
-    LegalNetworkConfig, LegalRedactionConfig,
+    LegalAuditConfig, LegalConfig, LegalEncryptionConfig, LegalHardeningProfile, LegalLlmRoute,
+    LegalLlmRoutingConfig, LegalNetworkConfig, LegalRedactionConfig, LegalStorageConfig,
 };
 pub use self::llm::{
-    AnthropicDirectConfig, LlmBackend, LlmConfig, NearAiConfig, OllamaConfig,
-    OpenAiCompatibleConfig, OpenAiDirectConfig, TinfoilConfig,
+    AnthropicDirectConfig, LlmBackend, LlmConfig, LlmRouteTarget, LocalModelCapabilities,
+    NearAiConfig, OllamaConfig, OpenAiCompatibleConfig, OpenAiDirectConfig, TinfoilConfig,
 };
 pub use self::routines::RoutineConfig;
 pub use self::safety::SafetyConfig;
 pub use self::sandbox::{ClaudeCodeConfig, SandboxModeConfig};
 pub use self::secrets::SecretsConfig;
 pub use self::skills::SkillsConfig;
+pub use self::transcription::{TranscriptionBackend, TranscriptionConfig};
 pub use self::tunnel::TunnelConfig;
 pub use self::wasm::WasmConfig;
 
@@ -75,11 +83,14 @@ pub struct Config {
     pub builder: BuilderModeConfig,
     pub heartbeat: HeartbeatConfig,
     pub hygiene: HygieneConfig,
+    pub git_mirror: GitMirrorConfig,
+    pub backup: BackupScheduleConfig,
     pub routines: RoutineConfig,
     pub sandbox: SandboxModeConfig,
     pub claude_code: ClaudeCodeConfig,
     pub skills: SkillsConfig,
     pub legal: LegalConfig,
+    pub transcription: TranscriptionConfig,
     pub observability: crate::observability::ObservabilityConfig,
 }
 
@@ -220,10 +231,17 @@ impl Config {
         } else {
             DatabaseConfig::resolve()?
         };
+        let llm = LlmConfig::resolve(settings)?;
+        let embeddings = EmbeddingsConfig::resolve(settings)?;
+        let transcription = TranscriptionConfig::resolve()?;
+        if llm.air_gapped {
+            embeddings.ensure_air_gapped()?;
+            transcription.ensure_air_gapped()?;
+        }
         Ok(Self {
             database,
-            llm: LlmConfig::resolve(settings)?,
-            embeddings: EmbeddingsConfig::resolve(settings)?,
+            llm,
+            embeddings,
             tunnel: TunnelConfig::resolve(settings)?,
             channels: ChannelsConfig::resolve(settings)?,
             agent: AgentConfig::resolve(settings)?,
@@ -233,13 +251,17 @@ impl Config {
             builder: BuilderModeConfig::resolve()?,
             heartbeat: HeartbeatConfig::resolve(settings)?,
             hygiene: HygieneConfig::resolve()?,
+            git_mirror: GitMirrorConfig::resolve()?,
+            backup: BackupScheduleConfig::resolve()?,
             routines: RoutineConfig::resolve()?,
             sandbox: SandboxModeConfig::resolve()?,
             claude_code: ClaudeCodeConfig::resolve()?,
             skills: SkillsConfig::resolve()?,
             legal: LegalConfig::resolve(settings)?,
+            transcription,
             observability: crate::observability::ObservabilityConfig {
                 backend: std::env::var("OBSERVABILITY_BACKEND").unwrap_or_else(|_| "none".into()),
+                metrics_enabled: helpers::parse_bool_env("METRICS_ENABLED", true)?,
             },
         })
     }
@@ -1,5 +1,38 @@
 use crate::config::helpers::{optional_env, parse_bool_env, parse_optional_env, parse_string_env};
 use crate::error::ConfigError;
+use crate::orchestrator::retry::RetryPolicy;
+
+/// Resolve a job retry policy from `{prefix}_RETRY_MAX_ATTEMPTS`,
+/// `{prefix}_RETRY_BACKOFF_SECS`, `{prefix}_RETRY_MAX_BACKOFF_SECS`, and
+/// `{prefix}_RETRY_ON` (comma-separated failure classes).
+fn resolve_retry_policy(prefix: &str, defaults: RetryPolicy) -> Result<RetryPolicy, ConfigError> {
+    let retry_on_key = format!("{prefix}_RETRY_ON");
+    let retry_on = match optional_env(&retry_on_key)? {
+        Some(value) => {
+            RetryPolicy::parse_retry_on(&value).map_err(|message| ConfigError::InvalidValue {
+                key: retry_on_key,
+                message,
+            })?
+        }
+        None => defaults.retry_on,
+    };
+    Ok(RetryPolicy {
+        max_attempts: parse_optional_env(
+            &format!("{prefix}_RETRY_MAX_ATTEMPTS"),
+            defaults.max_attempts,
+        )?
+        .max(1),
+        initial_backoff: std::time::Duration::from_secs(parse_optional_env(
+            &format!("{prefix}_RETRY_BACKOFF_SECS"),
+            defaults.initial_backoff.as_secs(),
+        )?),
+        max_backoff: std::time::Duration::from_secs(parse_optional_env(
+            &format!("{prefix}_RETRY_MAX_BACKOFF_SECS"),
+            defaults.max_backoff.as_secs(),
+        )?),
+        retry_on,
+    })
+}
 
 /// Docker sandbox configuration.
 #[derive(Debug, Clone)]
@@ -20,6 +53,15 @@ pub struct SandboxModeConfig {
     pub auto_pull_image: bool,
     /// Additional domains to allow through the network proxy.
     pub extra_allowed_domains: Vec<String>,
+    /// Job containers allowed to run at once; further jobs are queued.
+    pub max_concurrent_jobs: usize,
+    /// Job containers a single user may run at once.
+    pub max_jobs_per_user: usize,
+    /// Retry policy for worker-mode jobs.
+    pub retry: RetryPolicy,
+    /// Days job artifacts are kept before the retention sweep deletes them
+    /// (0 keeps them forever).
+    pub artifact_retention_days: u32,
 }
 
 impl Default for SandboxModeConfig {
@@ -33,6 +75,10 @@ impl Default for SandboxModeConfig {
             image: "clawyer-worker:latest".to_string(),
             auto_pull_image: true,
             extra_allowed_domains: Vec::new(),
+            max_concurrent_jobs: 4,
+            max_jobs_per_user: 2,
+            retry: RetryPolicy::default(),
+            artifact_retention_days: 30,
         }
     }
 }
@@ -52,6 +98,10 @@ impl SandboxModeConfig {
             image: parse_string_env("SANDBOX_IMAGE", "clawyer-worker:latest")?,
             auto_pull_image: parse_bool_env("SANDBOX_AUTO_PULL", true)?,
             extra_allowed_domains: extra_domains,
+            max_concurrent_jobs: parse_optional_env("SANDBOX_MAX_CONCURRENT_JOBS", 4)?,
+            max_jobs_per_user: parse_optional_env("SANDBOX_MAX_JOBS_PER_USER", 2)?,
+            retry: resolve_retry_policy("SANDBOX", RetryPolicy::default())?,
+            artifact_retention_days: parse_optional_env("SANDBOX_ARTIFACT_RETENTION_DAYS", 30)?,
         })
     }
 
@@ -102,6 +152,8 @@ pub struct ClaudeCodeConfig {
     ///
     /// Patterns follow Claude Code syntax: `"Bash(*)"`, `"Read"`, `"Edit(*)"`, etc.
     pub allowed_tools: Vec<String>,
+    /// Retry policy for Claude Code jobs.
+    pub retry: RetryPolicy,
 }
 
 /// Default allowed tools for Claude Code inside containers.
@@ -142,6 +194,11 @@ impl Default for ClaudeCodeConfig {
             max_turns: 50,
             memory_limit_mb: 4096,
             allowed_tools: default_claude_code_allowed_tools(),
+            // Claude Code runs are long; retry fewer times than workers.
+            retry: RetryPolicy {
+                max_attempts: 2,
+                ..RetryPolicy::default()
+            },
         }
     }
 }
@@ -224,6 +281,7 @@ impl ClaudeCodeConfig {
                         .collect()
                 })
                 .unwrap_or(defaults.allowed_tools),
+            retry: resolve_retry_policy("CLAUDE_CODE", defaults.retry)?,
         })
     }
 }
@@ -0,0 +1,248 @@
+use std::sync::Arc;
+
+use secrecy::SecretString;
+
+use crate::config::helpers::optional_env;
+use crate::error::ConfigError;
+use crate::integrations::transcription::{Transcriber, WhisperApiTranscriber};
+
+/// Which backend transcribes channel voice notes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptionBackend {
+    /// OpenAI-compatible `/audio/transcriptions` endpoint.
+    WhisperApi,
+    /// Local `whisper.cpp` CLI (requires the `local-transcription` feature).
+    WhisperCpp,
+}
+
+/// Voice note transcription configuration.
+///
+/// Transcription is off unless `TRANSCRIPTION_BACKEND` is set; channels then
+/// emit a placeholder instead of the transcript.
+#[derive(Debug, Clone)]
+pub struct TranscriptionConfig {
+    /// Env: `TRANSCRIPTION_BACKEND` (`whisper_api`, `whisper_cpp`, or `none`).
+    pub backend: Option<TranscriptionBackend>,
+    /// Env: `TRANSCRIPTION_BASE_URL` (default: `https://api.openai.com/v1`).
+    pub api_base_url: String,
+    /// Env: `TRANSCRIPTION_API_KEY`, falling back to `OPENAI_API_KEY`.
+    pub api_key: Option<SecretString>,
+    /// Env: `TRANSCRIPTION_MODEL` (default: `whisper-1`).
+    pub model: String,
+    /// ISO-639-1 language hint. Env: `TRANSCRIPTION_LANGUAGE`.
+    pub language: Option<String>,
+    /// Env: `WHISPER_CPP_BIN` (default: `whisper-cli`).
+    pub whisper_cpp_bin: String,
+    /// Path to a ggml model file. Env: `WHISPER_CPP_MODEL`.
+    pub whisper_cpp_model: Option<std::path::PathBuf>,
+    /// Used to convert channel audio to 16 kHz WAV. Env: `FFMPEG_BIN` (default: `ffmpeg`).
+    pub ffmpeg_bin: String,
+}
+
+impl Default for TranscriptionConfig {
+    fn default() -> Self {
+        Self {
+            backend: None,
+            api_base_url: "https://api.openai.com/v1".to_string(),
+            api_key: None,
+            model: "whisper-1".to_string(),
+            language: None,
+            whisper_cpp_bin: "whisper-cli".to_string(),
+            whisper_cpp_model: None,
+            ffmpeg_bin: "ffmpeg".to_string(),
+        }
+    }
+}
+
+impl TranscriptionConfig {
+    pub(crate) fn resolve() -> Result<Self, ConfigError> {
+        let defaults = Self::default();
+        let backend = match optional_env("TRANSCRIPTION_BACKEND")?
+            .map(|s| s.to_lowercase())
+            .as_deref()
+        {
+            None | Some("none") => None,
+            Some("whisper_api") | Some("openai") => Some(TranscriptionBackend::WhisperApi),
+            Some("whisper_cpp") | Some("local") => Some(TranscriptionBackend::WhisperCpp),
+            Some(other) => {
+                return Err(ConfigError::InvalidValue {
+                    key: "TRANSCRIPTION_BACKEND".to_string(),
+                    message: format!(
+                        "must be 'whisper_api', 'whisper_cpp', or 'none', got '{other}'"
+                    ),
+                });
+            }
+        };
+
+        let api_key = match optional_env("TRANSCRIPTION_API_KEY")? {
+            Some(key) => Some(key),
+            None => optional_env("OPENAI_API_KEY")?,
+        }
+        .map(SecretString::from);
+
+        Ok(Self {
+            backend,
+            api_base_url: optional_env("TRANSCRIPTION_BASE_URL")?.unwrap_or(defaults.api_base_url),
+            api_key,
+            model: optional_env("TRANSCRIPTION_MODEL")?.unwrap_or(defaults.model),
+            language: optional_env("TRANSCRIPTION_LANGUAGE")?,
+            whisper_cpp_bin: optional_env("WHISPER_CPP_BIN")?.unwrap_or(defaults.whisper_cpp_bin),
+            whisper_cpp_model: optional_env("WHISPER_CPP_MODEL")?.map(std::path::PathBuf::from),
+            ffmpeg_bin: optional_env("FFMPEG_BIN")?.unwrap_or(defaults.ffmpeg_bin),
+        })
+    }
+
+    /// Under `LLM_AIR_GAPPED`, voice notes may only go to whisper.cpp or a
+    /// Whisper-compatible server on a local address.
+    pub(crate) fn ensure_air_gapped(&self) -> Result<(), ConfigError> {
+        match self.backend {
+            Some(TranscriptionBackend::WhisperApi)
+                if !crate::config::llm::is_local_endpoint(&self.api_base_url) =>
+            {
+                Err(ConfigError::InvalidValue {
+                    key: "TRANSCRIPTION_BASE_URL".to_string(),
+                    message: format!(
+                        "LLM_AIR_GAPPED requires a local transcription endpoint, not '{}'",
+                        self.api_base_url
+                    ),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Create the configured transcriber, or `None` when transcription is
+    /// disabled or the backend is missing something it needs.
+    pub fn create_transcriber(&self) -> Option<Arc<dyn Transcriber>> {
+        match self.backend? {
+            TranscriptionBackend::WhisperApi => {
+                if self.api_key.is_none()
+                    && !crate::config::llm::is_local_endpoint(&self.api_base_url)
+                {
+                    tracing::warn!(
+                        "Transcription configured but neither TRANSCRIPTION_API_KEY nor OPENAI_API_KEY is set"
+                    );
+                    return None;
+                }
+                tracing::info!(
+                    "Voice note transcription enabled via Whisper API (model: {}, base: {})",
+                    self.model,
+                    self.api_base_url
+                );
+                Some(Arc::new(WhisperApiTranscriber::new(
+                    self.api_base_url.clone(),
+                    self.api_key.clone(),
+                    self.model.clone(),
+                    self.language.clone(),
+                )))
+            }
+            TranscriptionBackend::WhisperCpp => self.create_local_transcriber(),
+        }
+    }
+
+    #[cfg(feature = "local-transcription")]
+    fn create_local_transcriber(&self) -> Option<Arc<dyn Transcriber>> {
+        let Some(model) = self.whisper_cpp_model.clone() else {
+            tracing::warn!("Local transcription configured but WHISPER_CPP_MODEL not set");
+            return None;
+        };
+        tracing::info!(
+            "Voice note transcription enabled via whisper.cpp (model: {})",
+            model.display()
+        );
+        Some(Arc::new(
+            crate::integrations::transcription::WhisperCppTranscriber::new(
+                self.whisper_cpp_bin.clone(),
+                model,
+                self.ffmpeg_bin.clone(),
+                self.language.clone(),
+            ),
+        ))
+    }
+
+    #[cfg(not(feature = "local-transcription"))]
+    fn create_local_transcriber(&self) -> Option<Arc<dyn Transcriber>> {
+        tracing::warn!(
+            "Local transcription configured but this build lacks the `local-transcription` feature"
+        );
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::helpers::ENV_MUTEX;
+
+    fn clear_transcription_env() {
+        // SAFETY: Only called under ENV_MUTEX in tests.
+        unsafe {
+            std::env::remove_var("TRANSCRIPTION_BACKEND");
+            std::env::remove_var("TRANSCRIPTION_BASE_URL");
+            std::env::remove_var("TRANSCRIPTION_API_KEY");
+            std::env::remove_var("TRANSCRIPTION_MODEL");
+            std::env::remove_var("TRANSCRIPTION_LANGUAGE");
+            std::env::remove_var("OPENAI_API_KEY");
+        }
+    }
+
+    #[test]
+    fn transcription_is_off_by_default() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_transcription_env();
+
+        let config = TranscriptionConfig::resolve().expect("resolve should succeed");
+        assert_eq!(config.backend, None);
+        assert_eq!(config.model, "whisper-1");
+        assert!(config.create_transcriber().is_none());
+    }
+
+    #[test]
+    fn whisper_api_backend_reads_env() {
+        let _guard = ENV_MUTEX.lock().expect("env mutex poisoned");
+        clear_transcription_env();
+        // SAFETY: Under ENV_MUTEX.
+        unsafe {
+            std::env::set_var("TRANSCRIPTION_BACKEND", "whisper_api");
+            std::env::set_var("TRANSCRIPTION_BASE_URL", "http://127.0.0.1:9000/v1");
+            std::env::set_var("TRANSCRIPTION_LANGUAGE", "en");
+        }
+
+        let config = TranscriptionConfig::resolve().expect("resolve should succeed");
+        assert_eq!(config.backend, Some(TranscriptionBackend::WhisperApi));
+        assert_eq!(config.api_base_url, "http://127.0.0.1:9000/v1");
+        assert_eq!(config.language.as_deref(), Some("en"));
+        // A local server doesn't need a key.
+        assert!(config.create_transcriber().is_some());
+
+        // SAFETY: Under ENV_MUTEX.
+        unsafe {
+            std::env::set_var("TRANSCRIPTION_BACKEND", "tape-recorder");
+        }
+        assert!(TranscriptionConfig::resolve().is_err());
+        clear_transcription_env();
+    }
+
+    #[test]
+    fn air_gap_rejects_hosted_whisper_api() {
+        let hosted = TranscriptionConfig {
+            backend: Some(TranscriptionBackend::WhisperApi),
+            ..Default::default()
+        };
+        assert!(hosted.ensure_air_gapped().is_err());
+
+        let local = TranscriptionConfig {
+            backend: Some(TranscriptionBackend::WhisperApi),
+            api_base_url: "http://localhost:8178/v1".to_string(),
+            ..Default::default()
+        };
+        assert!(local.ensure_air_gapped().is_ok());
+
+        let cpp = TranscriptionConfig {
+            backend: Some(TranscriptionBackend::WhisperCpp),
+            ..Default::default()
+        };
+        assert!(cpp.ensure_air_gapped().is_ok());
+        assert!(TranscriptionConfig::default().ensure_air_gapped().is_ok());
+    }
+}
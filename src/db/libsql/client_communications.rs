@@ -0,0 +1,308 @@
+//! ClientCommunicationStore implementation for LibSqlBackend.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use libsql::params;
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+
+use super::{LibSqlBackend, fmt_ts, get_json, get_opt_text, get_text, get_ts, opt_text};
+use crate::db::{
+    ClientCommunicationConsentRecord, ClientCommunicationPreferencesRecord,
+    ClientCommunicationStore, CommunicationChannel, CommunicationConsentStatus,
+    CreateClientCommunicationConsentParams, UpsertClientCommunicationPreferencesParams,
+};
+use crate::error::DatabaseError;
+
+const PREFERENCES_COLUMNS: &str = "user_id, client_id, allowed_channels, quiet_hours, timezone, \
+     language, disclaimers, updated_by, created_at, updated_at";
+
+const CONSENT_COLUMNS: &str =
+    "id, user_id, client_id, channel, status, source, consented_at, recorded_by, created_at";
+
+fn parse_id(raw: &str) -> Result<Uuid, DatabaseError> {
+    raw.parse()
+        .map_err(|e: uuid::Error| DatabaseError::Serialization(e.to_string()))
+}
+
+fn json_column<T: DeserializeOwned>(
+    row: &libsql::Row,
+    idx: i32,
+    field: &str,
+) -> Result<T, DatabaseError> {
+    serde_json::from_value(get_json(row, idx))
+        .map_err(|e| DatabaseError::Serialization(format!("invalid {field}: {e}")))
+}
+
+fn to_json_text<T: serde::Serialize>(value: &T) -> Result<String, DatabaseError> {
+    serde_json::to_string(value).map_err(|e| DatabaseError::Serialization(e.to_string()))
+}
+
+fn row_to_preferences(
+    row: &libsql::Row,
+) -> Result<ClientCommunicationPreferencesRecord, DatabaseError> {
+    Ok(ClientCommunicationPreferencesRecord {
+        user_id: get_text(row, 0),
+        client_id: parse_id(&get_text(row, 1))?,
+        allowed_channels: json_column(row, 2, "allowed_channels")?,
+        quiet_hours: json_column(row, 3, "quiet_hours")?,
+        timezone: get_opt_text(row, 4),
+        language: get_opt_text(row, 5),
+        disclaimers: json_column(row, 6, "disclaimers")?,
+        updated_by: get_text(row, 7),
+        created_at: get_ts(row, 8),
+        updated_at: get_ts(row, 9),
+    })
+}
+
+fn row_to_consent(row: &libsql::Row) -> Result<ClientCommunicationConsentRecord, DatabaseError> {
+    let channel_raw = get_text(row, 3);
+    let status_raw = get_text(row, 4);
+    Ok(ClientCommunicationConsentRecord {
+        id: parse_id(&get_text(row, 0))?,
+        user_id: get_text(row, 1),
+        client_id: parse_id(&get_text(row, 2))?,
+        channel: CommunicationChannel::from_db_value(&channel_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("unknown communication channel '{channel_raw}'"))
+        })?,
+        status: CommunicationConsentStatus::from_db_value(&status_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("unknown consent status '{status_raw}'"))
+        })?,
+        source: get_text(row, 5),
+        consented_at: get_ts(row, 6),
+        recorded_by: get_text(row, 7),
+        created_at: get_ts(row, 8),
+    })
+}
+
+#[async_trait]
+impl ClientCommunicationStore for LibSqlBackend {
+    async fn get_client_communication_preferences(
+        &self,
+        user_id: &str,
+        client_id: Uuid,
+    ) -> Result<Option<ClientCommunicationPreferencesRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {PREFERENCES_COLUMNS} FROM client_communication_preferences \
+                     WHERE user_id = ?1 AND client_id = ?2"
+                ),
+                params![user_id, client_id.to_string()],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        row_to_preferences(&row).map(Some)
+    }
+
+    async fn upsert_client_communication_preferences(
+        &self,
+        user_id: &str,
+        client_id: Uuid,
+        input: &UpsertClientCommunicationPreferencesParams,
+    ) -> Result<ClientCommunicationPreferencesRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        let now = fmt_ts(&Utc::now());
+        conn.execute(
+            "INSERT INTO client_communication_preferences \
+             (user_id, client_id, allowed_channels, quiet_hours, timezone, language, \
+              disclaimers, updated_by, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?9) \
+             ON CONFLICT (user_id, client_id) DO UPDATE SET \
+               allowed_channels = excluded.allowed_channels, \
+               quiet_hours = excluded.quiet_hours, \
+               timezone = excluded.timezone, \
+               language = excluded.language, \
+               disclaimers = excluded.disclaimers, \
+               updated_by = excluded.updated_by, \
+               updated_at = excluded.updated_at",
+            params![
+                user_id,
+                client_id.to_string(),
+                to_json_text(&input.allowed_channels)?,
+                to_json_text(&input.quiet_hours)?,
+                opt_text(input.timezone.as_deref()),
+                opt_text(input.language.as_deref()),
+                to_json_text(&input.disclaimers)?,
+                input.updated_by.as_str(),
+                now,
+            ],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        self.get_client_communication_preferences(user_id, client_id)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound {
+                entity: "client_communication_preferences".to_string(),
+                id: client_id.to_string(),
+            })
+    }
+
+    async fn create_client_communication_consent(
+        &self,
+        user_id: &str,
+        client_id: Uuid,
+        input: &CreateClientCommunicationConsentParams,
+    ) -> Result<ClientCommunicationConsentRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "INSERT INTO client_communication_consents \
+                     (id, user_id, client_id, channel, status, source, consented_at, \
+                      recorded_by, created_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9) \
+                     RETURNING {CONSENT_COLUMNS}"
+                ),
+                params![
+                    Uuid::new_v4().to_string(),
+                    user_id,
+                    client_id.to_string(),
+                    input.channel.as_str(),
+                    input.status.as_str(),
+                    input.source.as_str(),
+                    fmt_ts(&input.consented_at),
+                    input.recorded_by.as_str(),
+                    fmt_ts(&Utc::now()),
+                ],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let row = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+            .ok_or_else(|| {
+                DatabaseError::Query("communication consent insert returned no row".to_string())
+            })?;
+        row_to_consent(&row)
+    }
+
+    async fn list_client_communication_consents(
+        &self,
+        user_id: &str,
+        client_id: Uuid,
+    ) -> Result<Vec<ClientCommunicationConsentRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {CONSENT_COLUMNS} FROM client_communication_consents \
+                     WHERE user_id = ?1 AND client_id = ?2 \
+                     ORDER BY consented_at DESC, created_at DESC"
+                ),
+                params![user_id, client_id.to_string()],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let mut consents = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            consents.push(row_to_consent(&row)?);
+        }
+        Ok(consents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::db::{
+        ClientType, CommunicationChannel, CommunicationConsentStatus,
+        CreateClientCommunicationConsentParams, CreateClientParams,
+        UpsertClientCommunicationPreferencesParams,
+    };
+
+    #[tokio::test]
+    async fn preferences_upsert_and_latest_consent_comes_first() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let client = db
+            .create_client(
+                "default",
+                &CreateClientParams {
+                    name: "Acme".to_string(),
+                    client_type: ClientType::Entity,
+                    email: None,
+                    phone: None,
+                    address: None,
+                    notes: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert!(
+            db.get_client_communication_preferences("default", client.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let mut input = UpsertClientCommunicationPreferencesParams {
+            allowed_channels: vec![CommunicationChannel::Email],
+            quiet_hours: Vec::new(),
+            timezone: Some("America/New_York".to_string()),
+            language: None,
+            disclaimers: vec!["Privileged & confidential.".to_string()],
+            updated_by: "default".to_string(),
+        };
+        let first = db
+            .upsert_client_communication_preferences("default", client.id, &input)
+            .await
+            .unwrap();
+        input.allowed_channels.push(CommunicationChannel::Sms);
+        input.language = Some("es".to_string());
+        let second = db
+            .upsert_client_communication_preferences("default", client.id, &input)
+            .await
+            .unwrap();
+        assert_eq!(second.created_at, first.created_at);
+        assert_eq!(
+            second.allowed_channels,
+            vec![CommunicationChannel::Email, CommunicationChannel::Sms]
+        );
+        assert_eq!(second.language.as_deref(), Some("es"));
+        assert_eq!(second.disclaimers, input.disclaimers);
+
+        let now = Utc::now();
+        for (status, at) in [
+            (CommunicationConsentStatus::Revoked, now),
+            (
+                CommunicationConsentStatus::Granted,
+                now - Duration::days(30),
+            ),
+        ] {
+            db.create_client_communication_consent(
+                "default",
+                client.id,
+                &CreateClientCommunicationConsentParams {
+                    channel: CommunicationChannel::Sms,
+                    status,
+                    source: "engagement letter".to_string(),
+                    consented_at: at,
+                    recorded_by: "default".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        }
+        let consents = db
+            .list_client_communication_consents("default", client.id)
+            .await
+            .unwrap();
+        assert_eq!(consents.len(), 2);
+        assert_eq!(consents[0].status, CommunicationConsentStatus::Revoked);
+        assert_eq!(consents[1].status, CommunicationConsentStatus::Granted);
+    }
+}
@@ -10,6 +10,17 @@ use crate::db::ConversationStore;
 use crate::error::DatabaseError;
 use crate::history::{ConversationMessage, ConversationSummary};
 
+impl LibSqlBackend {
+    /// First user message as a conversation title, cut to 100 characters
+    /// after opening (sealed messages are selected in full).
+    fn conversation_title(&self, stored: Option<String>) -> Result<Option<String>, DatabaseError> {
+        Ok(stored
+            .map(|stored| self.open_db(stored))
+            .transpose()?
+            .map(|title| title.chars().take(100).collect()))
+    }
+}
+
 #[async_trait]
 impl ConversationStore for LibSqlBackend {
     async fn create_conversation(
@@ -50,9 +61,10 @@ impl ConversationStore for LibSqlBackend {
         let conn = self.connect().await?;
         let id = Uuid::new_v4();
         let now = fmt_ts(&Utc::now());
+        let stored = self.seal_db(content)?;
         conn.execute(
                 "INSERT INTO conversation_messages (id, conversation_id, role, content, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![id.to_string(), conversation_id.to_string(), role, content, now],
+                params![id.to_string(), conversation_id.to_string(), role, stored.as_ref(), now],
             )
             .await
             .map_err(|e| DatabaseError::Query(e.to_string()))?;
@@ -99,7 +111,7 @@ impl ConversationStore for LibSqlBackend {
                     c.matter_id,
                     c.metadata,
                     (SELECT COUNT(*) FROM conversation_messages m WHERE m.conversation_id = c.id) AS message_count,
-                    (SELECT substr(m2.content, 1, 100)
+                    (SELECT CASE WHEN ?4 THEN m2.content ELSE substr(m2.content, 1, 100) END
                      FROM conversation_messages m2
                      WHERE m2.conversation_id = c.id AND m2.role = 'user'
                      ORDER BY m2.created_at ASC, m2.rowid ASC
@@ -110,7 +122,7 @@ impl ConversationStore for LibSqlBackend {
                 ORDER BY c.last_activity DESC
                 LIMIT ?3
                 "#,
-                params![user_id, channel, limit],
+                params![user_id, channel, limit, self.content_encryption_enabled()],
             )
             .await
             .map_err(|e| DatabaseError::Query(e.to_string()))?;
@@ -136,7 +148,7 @@ impl ConversationStore for LibSqlBackend {
                 last_activity: get_ts(&row, 2),
                 matter_id: get_opt_text(&row, 3),
                 message_count: get_i64(&row, 5),
-                title: get_opt_text(&row, 6),
+                title: self.conversation_title(get_opt_text(&row, 6))?,
                 thread_type,
             });
         }
@@ -161,7 +173,7 @@ impl ConversationStore for LibSqlBackend {
                     c.matter_id,
                     c.metadata,
                     (SELECT COUNT(*) FROM conversation_messages m WHERE m.conversation_id = c.id) AS message_count,
-                    (SELECT substr(m2.content, 1, 100)
+                    (SELECT CASE WHEN ?5 THEN m2.content ELSE substr(m2.content, 1, 100) END
                      FROM conversation_messages m2
                      WHERE m2.conversation_id = c.id AND m2.role = 'user'
                      ORDER BY m2.created_at ASC, m2.rowid ASC
@@ -174,7 +186,13 @@ impl ConversationStore for LibSqlBackend {
                 ORDER BY c.last_activity DESC
                 LIMIT ?4
                 "#,
-                params![user_id, channel, matter_id, limit],
+                params![
+                    user_id,
+                    channel,
+                    matter_id,
+                    limit,
+                    self.content_encryption_enabled()
+                ],
             )
             .await
             .map_err(|e| DatabaseError::Query(e.to_string()))?;
@@ -200,7 +218,7 @@ impl ConversationStore for LibSqlBackend {
                 last_activity: get_ts(&row, 2),
                 matter_id: get_opt_text(&row, 3),
                 message_count: get_i64(&row, 5),
-                title: get_opt_text(&row, 6),
+                title: self.conversation_title(get_opt_text(&row, 6))?,
                 thread_type,
             });
         }
@@ -313,7 +331,7 @@ impl ConversationStore for LibSqlBackend {
             all.push(ConversationMessage {
                 id: get_text(&row, 0).parse().unwrap_or_default(),
                 role: get_text(&row, 1),
-                content: get_text(&row, 2),
+                content: self.open_db(get_text(&row, 2))?,
                 created_at: get_ts(&row, 3),
             });
         }
@@ -392,7 +410,7 @@ impl ConversationStore for LibSqlBackend {
             messages.push(ConversationMessage {
                 id: get_text(&row, 0).parse().unwrap_or_default(),
                 role: get_text(&row, 1),
-                content: get_text(&row, 2),
+                content: self.open_db(get_text(&row, 2))?,
                 created_at: get_ts(&row, 3),
             });
         }
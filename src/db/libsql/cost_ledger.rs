@@ -0,0 +1,310 @@
+//! CostLedgerStore implementation for LibSqlBackend.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use libsql::params;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use super::{
+    LibSqlBackend, fmt_opt_ts, fmt_ts, get_decimal, get_i64, get_opt_text, get_text, get_ts,
+    opt_text, opt_text_owned,
+};
+use crate::db::{
+    CostEntryRecord, CostLedgerStore, CostSource, MatterSpendCapRecord, RecordCostEntryParams,
+};
+use crate::error::DatabaseError;
+
+const COST_ENTRY_COLUMNS: &str = "id, user_id, matter_id, client_id, source, job_id, label, \
+     input_tokens, output_tokens, cost, created_at";
+
+fn row_to_cost_entry(row: &libsql::Row) -> Result<CostEntryRecord, DatabaseError> {
+    let source_raw = get_text(row, 4);
+    let source = CostSource::from_db_value(&source_raw).ok_or_else(|| {
+        DatabaseError::Serialization(format!("invalid cost source '{source_raw}'"))
+    })?;
+    Ok(CostEntryRecord {
+        id: get_text(row, 0)
+            .parse()
+            .map_err(|e: uuid::Error| DatabaseError::Serialization(e.to_string()))?,
+        user_id: get_text(row, 1),
+        matter_id: get_opt_text(row, 2),
+        client_id: get_opt_text(row, 3).and_then(|s| s.parse().ok()),
+        source,
+        job_id: get_opt_text(row, 5).and_then(|s| s.parse().ok()),
+        label: get_text(row, 6),
+        input_tokens: get_i64(row, 7),
+        output_tokens: get_i64(row, 8),
+        cost: get_decimal(row, 9),
+        created_at: get_ts(row, 10),
+    })
+}
+
+fn row_to_spend_cap(row: &libsql::Row) -> MatterSpendCapRecord {
+    MatterSpendCapRecord {
+        user_id: get_text(row, 0),
+        matter_id: get_text(row, 1),
+        monthly_cap_cents: get_i64(row, 2),
+        updated_at: get_ts(row, 3),
+    }
+}
+
+#[async_trait]
+impl CostLedgerStore for LibSqlBackend {
+    async fn record_cost_entry(
+        &self,
+        params: &RecordCostEntryParams,
+    ) -> Result<CostEntryRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+        conn.execute(
+            &format!(
+                "INSERT INTO cost_entries ({COST_ENTRY_COLUMNS}) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"
+            ),
+            params![
+                id.to_string(),
+                params.user_id.as_str(),
+                opt_text(params.matter_id.as_deref()),
+                opt_text_owned(params.client_id.map(|id| id.to_string())),
+                params.source.as_str(),
+                opt_text_owned(params.job_id.map(|id| id.to_string())),
+                params.label.as_str(),
+                i64::from(params.input_tokens),
+                i64::from(params.output_tokens),
+                params.cost.to_string(),
+                fmt_ts(&now),
+            ],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(CostEntryRecord {
+            id,
+            user_id: params.user_id.clone(),
+            matter_id: params.matter_id.clone(),
+            client_id: params.client_id,
+            source: params.source,
+            job_id: params.job_id,
+            label: params.label.clone(),
+            input_tokens: i64::from(params.input_tokens),
+            output_tokens: i64::from(params.output_tokens),
+            cost: params.cost,
+            created_at: now,
+        })
+    }
+
+    async fn list_cost_entries(
+        &self,
+        user_id: &str,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CostEntryRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {COST_ENTRY_COLUMNS} FROM cost_entries \
+                     WHERE user_id = ?1 \
+                       AND (?2 IS NULL OR created_at >= ?2) \
+                       AND (?3 IS NULL OR created_at < ?3) \
+                     ORDER BY created_at ASC"
+                ),
+                params![user_id, fmt_opt_ts(&since), fmt_opt_ts(&until)],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let mut entries = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            entries.push(row_to_cost_entry(&row)?);
+        }
+        Ok(entries)
+    }
+
+    async fn matter_cost_since(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Decimal, DatabaseError> {
+        let conn = self.connect().await?;
+        // Costs are stored as text to keep decimal precision, so sum here.
+        let mut rows = conn
+            .query(
+                "SELECT cost FROM cost_entries \
+                 WHERE user_id = ?1 AND matter_id = ?2 AND created_at >= ?3",
+                params![user_id, matter_id, fmt_ts(&since)],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let mut total = Decimal::ZERO;
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            total += get_decimal(&row, 0);
+        }
+        Ok(total)
+    }
+
+    async fn set_matter_spend_cap(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        monthly_cap_cents: Option<i64>,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.connect().await?;
+        match monthly_cap_cents {
+            Some(cap) => conn
+                .execute(
+                    "INSERT INTO matter_spend_caps (user_id, matter_id, monthly_cap_cents, updated_at) \
+                     VALUES (?1, ?2, ?3, ?4) \
+                     ON CONFLICT (user_id, matter_id) DO UPDATE SET \
+                       monthly_cap_cents = excluded.monthly_cap_cents, \
+                       updated_at = excluded.updated_at",
+                    params![user_id, matter_id, cap, fmt_ts(&Utc::now())],
+                )
+                .await
+                .map_err(|e| DatabaseError::Query(e.to_string()))?,
+            None => conn
+                .execute(
+                    "DELETE FROM matter_spend_caps WHERE user_id = ?1 AND matter_id = ?2",
+                    params![user_id, matter_id],
+                )
+                .await
+                .map_err(|e| DatabaseError::Query(e.to_string()))?,
+        };
+        Ok(())
+    }
+
+    async fn get_matter_spend_cap(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<Option<MatterSpendCapRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                "SELECT user_id, matter_id, monthly_cap_cents, updated_at \
+                 FROM matter_spend_caps WHERE user_id = ?1 AND matter_id = ?2",
+                params![user_id, matter_id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+            .map(|row| row_to_spend_cap(&row)))
+    }
+
+    async fn list_matter_spend_caps(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<MatterSpendCapRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                "SELECT user_id, matter_id, monthly_cap_cents, updated_at \
+                 FROM matter_spend_caps WHERE user_id = ?1 ORDER BY matter_id",
+                params![user_id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let mut caps = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            caps.push(row_to_spend_cap(&row));
+        }
+        Ok(caps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use rust_decimal_macros::dec;
+
+    use crate::db::{CostSource, RecordCostEntryParams};
+
+    fn entry(matter_id: Option<&str>, cost: rust_decimal::Decimal) -> RecordCostEntryParams {
+        RecordCostEntryParams {
+            user_id: "default".to_string(),
+            matter_id: matter_id.map(str::to_string),
+            client_id: None,
+            source: CostSource::LlmCall,
+            job_id: None,
+            label: "gpt-4o".to_string(),
+            input_tokens: 100,
+            output_tokens: 50,
+            cost,
+        }
+    }
+
+    #[tokio::test]
+    async fn ledger_sums_matter_spend_and_stores_caps() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let start = Utc::now() - Duration::seconds(1);
+
+        db.record_cost_entry(&entry(Some("acme"), dec!(0.125)))
+            .await
+            .unwrap();
+        db.record_cost_entry(&entry(Some("acme"), dec!(0.250)))
+            .await
+            .unwrap();
+        db.record_cost_entry(&entry(None, dec!(1))).await.unwrap();
+
+        assert_eq!(
+            db.matter_cost_since("default", "acme", start)
+                .await
+                .unwrap(),
+            dec!(0.375)
+        );
+        let entries = db
+            .list_cost_entries("default", Some(start), None)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].source, CostSource::LlmCall);
+        assert!(
+            db.list_cost_entries("default", None, Some(start))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            db.list_cost_entries("other", None, None)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        db.set_matter_spend_cap("default", "acme", Some(5_000))
+            .await
+            .unwrap();
+        db.set_matter_spend_cap("default", "acme", Some(2_500))
+            .await
+            .unwrap();
+        let cap = db.get_matter_spend_cap("default", "acme").await.unwrap();
+        assert_eq!(cap.map(|c| c.monthly_cap_cents), Some(2_500));
+        assert_eq!(db.list_matter_spend_caps("default").await.unwrap().len(), 1);
+
+        db.set_matter_spend_cap("default", "acme", None)
+            .await
+            .unwrap();
+        assert!(
+            db.get_matter_spend_cap("default", "acme")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! DocumentEntityStore implementation for LibSqlBackend.
+
+use async_trait::async_trait;
+use libsql::params;
+use uuid::Uuid;
+
+use super::{LibSqlBackend, get_opt_text, get_text, get_ts, opt_text};
+use crate::db::{
+    CreateDocumentEntityParams, DocumentEntityKind, DocumentEntityRecord, DocumentEntityStore,
+};
+use crate::error::DatabaseError;
+
+const DOCUMENT_ENTITY_COLUMNS: &str = "id, user_id, matter_id, kind, value, normalized_value, \
+     source_path, snippet, confidence, extractor, created_at";
+
+fn row_to_document_entity(row: &libsql::Row) -> Result<DocumentEntityRecord, DatabaseError> {
+    let id_raw = get_text(row, 0);
+    let id = id_raw.parse::<Uuid>().map_err(|e| {
+        DatabaseError::Serialization(format!("invalid document entity id '{id_raw}': {e}"))
+    })?;
+    let kind_raw = get_text(row, 3);
+    let kind = DocumentEntityKind::from_db_value(&kind_raw).ok_or_else(|| {
+        DatabaseError::Serialization(format!("invalid document entity kind '{kind_raw}'"))
+    })?;
+    Ok(DocumentEntityRecord {
+        id,
+        user_id: get_text(row, 1),
+        matter_id: get_text(row, 2),
+        kind,
+        value: get_text(row, 4),
+        normalized_value: get_text(row, 5),
+        source_path: get_text(row, 6),
+        snippet: get_opt_text(row, 7),
+        confidence: row.get::<f64>(8).unwrap_or_default(),
+        extractor: get_text(row, 9),
+        created_at: get_ts(row, 10),
+    })
+}
+
+#[async_trait]
+impl DocumentEntityStore for LibSqlBackend {
+    async fn replace_document_entities(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        source_path: &str,
+        entities: &[CreateDocumentEntityParams],
+    ) -> Result<usize, DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute("BEGIN", ()).await?;
+
+        let op_result: Result<usize, DatabaseError> = async {
+            conn.execute(
+                "DELETE FROM document_entities \
+                 WHERE user_id = ?1 AND matter_id = ?2 AND source_path = ?3",
+                params![user_id, matter_id, source_path],
+            )
+            .await?;
+            let mut inserted = 0;
+            for entity in entities {
+                inserted += conn
+                    .execute(
+                        "INSERT INTO document_entities \
+                         (id, user_id, matter_id, kind, value, normalized_value, source_path, \
+                          snippet, confidence, extractor) \
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10) \
+                         ON CONFLICT (user_id, matter_id, source_path, kind, normalized_value) \
+                         DO NOTHING",
+                        params![
+                            Uuid::new_v4().to_string(),
+                            user_id,
+                            matter_id,
+                            entity.kind.as_str(),
+                            entity.value.as_str(),
+                            entity.normalized_value.as_str(),
+                            source_path,
+                            opt_text(entity.snippet.as_deref()),
+                            entity.confidence,
+                            entity.extractor.as_str(),
+                        ],
+                    )
+                    .await? as usize;
+            }
+            Ok(inserted)
+        }
+        .await;
+
+        match op_result {
+            Ok(inserted) => {
+                conn.execute("COMMIT", ()).await?;
+                Ok(inserted)
+            }
+            Err(err) => {
+                let _ = conn.execute("ROLLBACK", ()).await;
+                Err(err)
+            }
+        }
+    }
+
+    async fn list_document_entities(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        kind: Option<DocumentEntityKind>,
+    ) -> Result<Vec<DocumentEntityRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {DOCUMENT_ENTITY_COLUMNS} FROM document_entities \
+                     WHERE user_id = ?1 AND matter_id = ?2 AND (?3 IS NULL OR kind = ?3) \
+                     ORDER BY kind, normalized_value, source_path"
+                ),
+                params![user_id, matter_id, opt_text(kind.map(|k| k.as_str()))],
+            )
+            .await?;
+        let mut records = Vec::new();
+        while let Some(row) = rows.next().await? {
+            records.push(row_to_document_entity(&row)?);
+        }
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::{CreateDocumentEntityParams, DocumentEntityKind};
+
+    fn entity(
+        kind: DocumentEntityKind,
+        value: &str,
+        normalized: &str,
+    ) -> CreateDocumentEntityParams {
+        CreateDocumentEntityParams {
+            kind,
+            value: value.to_string(),
+            normalized_value: normalized.to_string(),
+            snippet: None,
+            confidence: 0.8,
+            extractor: "heuristic".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn replacing_document_entities_drops_stale_mentions() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let path = "matters/acme/contracts/msa.md";
+
+        let inserted = db
+            .replace_document_entities(
+                "default",
+                "acme",
+                path,
+                &[
+                    entity(DocumentEntityKind::Organization, "Globex LLC", "globex llc"),
+                    entity(DocumentEntityKind::Amount, "$1,200", "USD 1200.00"),
+                    entity(DocumentEntityKind::Amount, "$1200.00", "USD 1200.00"),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(inserted, 2, "duplicate normalized values collapse");
+
+        db.replace_document_entities(
+            "default",
+            "acme",
+            path,
+            &[entity(
+                DocumentEntityKind::Date,
+                "March 3, 2023",
+                "2023-03-03",
+            )],
+        )
+        .await
+        .unwrap();
+        let all = db
+            .list_document_entities("default", "acme", None)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].kind, DocumentEntityKind::Date);
+        assert_eq!(all[0].source_path, path);
+
+        assert!(
+            db.list_document_entities("default", "acme", Some(DocumentEntityKind::Amount))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            db.list_document_entities("default", "other", None)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
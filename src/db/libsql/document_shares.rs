@@ -0,0 +1,336 @@
+//! DocumentShareStore implementation for LibSqlBackend.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use libsql::params;
+use uuid::Uuid;
+
+use super::{
+    LibSqlBackend, fmt_ts, get_i64, get_opt_bool, get_opt_text, get_opt_ts, get_text, get_ts,
+    opt_text,
+};
+use crate::db::{DocumentShareLinkRecord, DocumentShareStore, NewDocumentShareLinkParams};
+use crate::error::DatabaseError;
+
+const SHARE_LINK_COLUMNS: &str = "id, user_id, path, matter_id, token_hash, password_hash, \
+     recipient, watermark, created_by, expires_at, revoked_at, download_count, \
+     last_downloaded_at, failed_password_attempts, last_password_failure_at, created_at";
+
+fn row_to_share_link(row: &libsql::Row) -> Result<DocumentShareLinkRecord, DatabaseError> {
+    Ok(DocumentShareLinkRecord {
+        id: get_text(row, 0)
+            .parse()
+            .map_err(|e: uuid::Error| DatabaseError::Serialization(e.to_string()))?,
+        user_id: get_text(row, 1),
+        path: get_text(row, 2),
+        matter_id: get_opt_text(row, 3),
+        token_hash: get_text(row, 4),
+        password_hash: get_opt_text(row, 5),
+        recipient: get_opt_text(row, 6),
+        watermark: get_opt_bool(row, 7).unwrap_or(true),
+        created_by: get_text(row, 8),
+        expires_at: get_ts(row, 9),
+        revoked_at: get_opt_ts(row, 10),
+        download_count: get_i64(row, 11),
+        last_downloaded_at: get_opt_ts(row, 12),
+        failed_password_attempts: get_i64(row, 13),
+        last_password_failure_at: get_opt_ts(row, 14),
+        created_at: get_ts(row, 15),
+    })
+}
+
+impl LibSqlBackend {
+    async fn query_share_links(
+        &self,
+        sql: &str,
+        values: impl libsql::params::IntoParams,
+    ) -> Result<Vec<DocumentShareLinkRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(sql, values)
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let mut links = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            links.push(row_to_share_link(&row)?);
+        }
+        Ok(links)
+    }
+}
+
+#[async_trait]
+impl DocumentShareStore for LibSqlBackend {
+    async fn create_document_share_link(
+        &self,
+        params: &NewDocumentShareLinkParams,
+    ) -> Result<DocumentShareLinkRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+        conn.execute(
+            "INSERT INTO document_share_links \
+             (id, user_id, path, matter_id, token_hash, password_hash, recipient, watermark, \
+              created_by, expires_at, created_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                id.to_string(),
+                params.user_id.as_str(),
+                params.path.as_str(),
+                opt_text(params.matter_id.as_deref()),
+                params.token_hash.as_str(),
+                opt_text(params.password_hash.as_deref()),
+                opt_text(params.recipient.as_deref()),
+                i64::from(params.watermark),
+                params.created_by.as_str(),
+                fmt_ts(&params.expires_at),
+                fmt_ts(&now),
+            ],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(DocumentShareLinkRecord {
+            id,
+            user_id: params.user_id.clone(),
+            path: params.path.clone(),
+            matter_id: params.matter_id.clone(),
+            token_hash: params.token_hash.clone(),
+            password_hash: params.password_hash.clone(),
+            recipient: params.recipient.clone(),
+            watermark: params.watermark,
+            created_by: params.created_by.clone(),
+            expires_at: params.expires_at,
+            revoked_at: None,
+            download_count: 0,
+            last_downloaded_at: None,
+            failed_password_attempts: 0,
+            last_password_failure_at: None,
+            created_at: now,
+        })
+    }
+
+    async fn get_document_share_link_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<DocumentShareLinkRecord>, DatabaseError> {
+        Ok(self
+            .query_share_links(
+                &format!(
+                    "SELECT {SHARE_LINK_COLUMNS} FROM document_share_links WHERE token_hash = ?1"
+                ),
+                params![token_hash],
+            )
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    async fn list_document_share_links(
+        &self,
+        user_id: &str,
+        path: Option<&str>,
+    ) -> Result<Vec<DocumentShareLinkRecord>, DatabaseError> {
+        self.query_share_links(
+            &format!(
+                "SELECT {SHARE_LINK_COLUMNS} FROM document_share_links \
+                 WHERE user_id = ?1 AND (?2 IS NULL OR path = ?2) \
+                 ORDER BY created_at DESC"
+            ),
+            params![user_id, opt_text(path)],
+        )
+        .await
+    }
+
+    async fn get_document_share_link(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<DocumentShareLinkRecord>, DatabaseError> {
+        Ok(self
+            .query_share_links(
+                &format!(
+                    "SELECT {SHARE_LINK_COLUMNS} FROM document_share_links \
+                     WHERE user_id = ?1 AND id = ?2"
+                ),
+                params![user_id, id.to_string()],
+            )
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    async fn revoke_document_share_link(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<DocumentShareLinkRecord>, DatabaseError> {
+        Ok(self
+            .query_share_links(
+                &format!(
+                    "UPDATE document_share_links SET revoked_at = COALESCE(revoked_at, ?3) \
+                     WHERE user_id = ?1 AND id = ?2 RETURNING {SHARE_LINK_COLUMNS}"
+                ),
+                params![user_id, id.to_string(), fmt_ts(&Utc::now())],
+            )
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    async fn record_document_share_download(&self, id: Uuid) -> Result<(), DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute(
+            "UPDATE document_share_links \
+             SET download_count = download_count + 1, last_downloaded_at = ?2, \
+                 failed_password_attempts = 0, last_password_failure_at = NULL \
+             WHERE id = ?1",
+            params![id.to_string(), fmt_ts(&Utc::now())],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn claim_document_share_password_attempt(
+        &self,
+        id: Uuid,
+        seen_attempts: i64,
+    ) -> Result<Option<DocumentShareLinkRecord>, DatabaseError> {
+        Ok(self
+            .query_share_links(
+                &format!(
+                    "UPDATE document_share_links \
+                     SET failed_password_attempts = failed_password_attempts + 1, \
+                         last_password_failure_at = ?2 \
+                     WHERE id = ?1 AND failed_password_attempts = ?3 \
+                     RETURNING {SHARE_LINK_COLUMNS}"
+                ),
+                params![id.to_string(), fmt_ts(&Utc::now()), seen_attempts],
+            )
+            .await?
+            .into_iter()
+            .next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::db::NewDocumentShareLinkParams;
+
+    fn link(path: &str, token_hash: &str) -> NewDocumentShareLinkParams {
+        NewDocumentShareLinkParams {
+            user_id: "default".to_string(),
+            path: path.to_string(),
+            matter_id: Some("acme".to_string()),
+            token_hash: token_hash.to_string(),
+            password_hash: None,
+            recipient: Some("co-counsel".to_string()),
+            watermark: true,
+            created_by: "default".to_string(),
+            expires_at: Utc::now() + Duration::hours(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn share_links_track_downloads_and_revocation() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let memo = db
+            .create_document_share_link(&link("matters/acme/drafts/memo.md", "hash-1"))
+            .await
+            .unwrap();
+        db.create_document_share_link(&link("matters/acme/exhibits/a.md", "hash-2"))
+            .await
+            .unwrap();
+
+        let found = db
+            .get_document_share_link_by_token_hash("hash-1")
+            .await
+            .unwrap()
+            .expect("lookup by token hash");
+        assert_eq!(found.id, memo.id);
+        assert!(found.watermark);
+        assert!(found.is_active(Utc::now()));
+        assert!(
+            db.get_document_share_link_by_token_hash("missing")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        db.record_document_share_download(memo.id).await.unwrap();
+        db.record_document_share_download(memo.id).await.unwrap();
+        let listed = db
+            .list_document_share_links("default", Some("matters/acme/drafts/memo.md"))
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].download_count, 2);
+        assert!(listed[0].last_downloaded_at.is_some());
+        assert_eq!(
+            db.list_document_share_links("default", None)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        assert!(
+            db.revoke_document_share_link("other", memo.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let revoked = db
+            .revoke_document_share_link("default", memo.id)
+            .await
+            .unwrap()
+            .expect("revoke own link");
+        assert!(!revoked.is_active(Utc::now()));
+    }
+
+    #[tokio::test]
+    async fn password_attempts_are_counted_until_a_download() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let link = db
+            .create_document_share_link(&link("matters/acme/drafts/memo.md", "hash-1"))
+            .await
+            .unwrap();
+        for attempt in 1..=6 {
+            let updated = db
+                .claim_document_share_password_attempt(link.id, attempt - 1)
+                .await
+                .unwrap()
+                .expect("known link");
+            assert_eq!(updated.failed_password_attempts, attempt);
+            assert!(updated.last_password_failure_at.is_some());
+            assert!(updated.is_active(Utc::now()), "failures never revoke");
+        }
+        assert!(
+            db.claim_document_share_password_attempt(link.id, 5)
+                .await
+                .unwrap()
+                .is_none(),
+            "a stale count loses the claim"
+        );
+
+        db.record_document_share_download(link.id).await.unwrap();
+        let reset = db
+            .get_document_share_link("default", link.id)
+            .await
+            .unwrap()
+            .expect("known link");
+        assert_eq!(reset.failed_password_attempts, 0);
+        assert!(reset.last_password_failure_at.is_none());
+        assert!(
+            db.claim_document_share_password_attempt(uuid::Uuid::new_v4(), 0)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
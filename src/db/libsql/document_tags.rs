@@ -0,0 +1,229 @@
+//! DocumentTagStore implementation for LibSqlBackend.
+
+use async_trait::async_trait;
+use libsql::params;
+use uuid::Uuid;
+
+use super::{LibSqlBackend, get_opt_text, get_text, get_ts, opt_text};
+use crate::db::{DocumentTagRecord, DocumentTagStore, SetDocumentTagParams};
+use crate::error::DatabaseError;
+
+const DOCUMENT_TAG_COLUMNS: &str = "id, user_id, path, key, value, created_by, created_at, \
+     updated_at";
+
+fn row_to_document_tag(row: &libsql::Row) -> Result<DocumentTagRecord, DatabaseError> {
+    let id_raw = get_text(row, 0);
+    let id = id_raw.parse::<Uuid>().map_err(|e| {
+        DatabaseError::Serialization(format!("invalid document tag id '{id_raw}': {e}"))
+    })?;
+    Ok(DocumentTagRecord {
+        id,
+        user_id: get_text(row, 1),
+        path: get_text(row, 2),
+        key: get_text(row, 3),
+        value: get_opt_text(row, 4),
+        created_by: get_text(row, 5),
+        created_at: get_ts(row, 6),
+        updated_at: get_ts(row, 7),
+    })
+}
+
+#[async_trait]
+impl DocumentTagStore for LibSqlBackend {
+    async fn set_document_tags(
+        &self,
+        user_id: &str,
+        path: &str,
+        tags: &[SetDocumentTagParams],
+        created_by: &str,
+    ) -> Result<Vec<DocumentTagRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute("BEGIN", ()).await?;
+
+        let op_result: Result<(), DatabaseError> = async {
+            for tag in tags {
+                conn.execute(
+                    "INSERT INTO document_tags (id, user_id, path, key, value, created_by) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6) \
+                     ON CONFLICT (user_id, path, key) DO UPDATE SET \
+                     value = excluded.value, updated_at = datetime('now')",
+                    params![
+                        Uuid::new_v4().to_string(),
+                        user_id,
+                        path,
+                        tag.key.as_str(),
+                        opt_text(tag.value.as_deref()),
+                        created_by,
+                    ],
+                )
+                .await?;
+            }
+            Ok(())
+        }
+        .await;
+
+        match op_result {
+            Ok(()) => {
+                conn.execute("COMMIT", ()).await?;
+                self.list_document_tags(user_id, path).await
+            }
+            Err(err) => {
+                let _ = conn.execute("ROLLBACK", ()).await;
+                Err(err)
+            }
+        }
+    }
+
+    async fn remove_document_tag(
+        &self,
+        user_id: &str,
+        path: &str,
+        key: &str,
+    ) -> Result<bool, DatabaseError> {
+        let conn = self.connect().await?;
+        let removed = conn
+            .execute(
+                "DELETE FROM document_tags WHERE user_id = ?1 AND path = ?2 AND key = ?3",
+                params![user_id, path, key],
+            )
+            .await?;
+        Ok(removed > 0)
+    }
+
+    async fn list_document_tags(
+        &self,
+        user_id: &str,
+        path: &str,
+    ) -> Result<Vec<DocumentTagRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {DOCUMENT_TAG_COLUMNS} FROM document_tags \
+                     WHERE user_id = ?1 AND path = ?2 ORDER BY key"
+                ),
+                params![user_id, path],
+            )
+            .await?;
+        let mut records = Vec::new();
+        while let Some(row) = rows.next().await? {
+            records.push(row_to_document_tag(&row)?);
+        }
+        Ok(records)
+    }
+
+    async fn list_tagged_documents(
+        &self,
+        user_id: &str,
+        path_prefix: Option<&str>,
+    ) -> Result<Vec<DocumentTagRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {DOCUMENT_TAG_COLUMNS} FROM document_tags \
+                     WHERE user_id = ?1 AND (?2 IS NULL OR substr(path, 1, length(?2)) = ?2) \
+                     ORDER BY path, key"
+                ),
+                params![user_id, opt_text(path_prefix)],
+            )
+            .await?;
+        let mut records = Vec::new();
+        while let Some(row) = rows.next().await? {
+            records.push(row_to_document_tag(&row)?);
+        }
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::SetDocumentTagParams;
+
+    fn tag(key: &str, value: Option<&str>) -> SetDocumentTagParams {
+        SetDocumentTagParams {
+            key: key.to_string(),
+            value: value.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn document_tags_upsert_by_key_and_filter_by_prefix() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let memo = "matters/acme/research/memo.md";
+        let email = "matters/acme/correspondence/email.md";
+
+        let tags = db
+            .set_document_tags(
+                "default",
+                memo,
+                &[
+                    tag("responsive", None),
+                    tag("privilege", Some("attorney-client")),
+                ],
+                "default",
+            )
+            .await
+            .unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].key, "privilege");
+
+        let tags = db
+            .set_document_tags(
+                "default",
+                memo,
+                &[tag("privilege", Some("work-product"))],
+                "default",
+            )
+            .await
+            .unwrap();
+        assert_eq!(tags.len(), 2, "re-tagging a key replaces its value");
+        assert_eq!(tags[0].value.as_deref(), Some("work-product"));
+
+        db.set_document_tags("default", email, &[tag("hot-doc", None)], "default")
+            .await
+            .unwrap();
+        db.set_document_tags(
+            "default",
+            "notes/todo.md",
+            &[tag("hot-doc", None)],
+            "default",
+        )
+        .await
+        .unwrap();
+        let tagged = db
+            .list_tagged_documents("default", Some("matters/acme/"))
+            .await
+            .unwrap();
+        assert_eq!(tagged.len(), 3);
+        assert_eq!(tagged[0].path, email);
+        assert_eq!(
+            db.list_tagged_documents("default", None)
+                .await
+                .unwrap()
+                .len(),
+            4
+        );
+
+        assert!(
+            db.remove_document_tag("default", memo, "responsive")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !db.remove_document_tag("default", memo, "responsive")
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            db.list_document_tags("default", memo).await.unwrap().len(),
+            1
+        );
+        assert!(
+            db.list_document_tags("other", memo)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
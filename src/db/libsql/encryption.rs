@@ -0,0 +1,401 @@
+//! Optional at-rest encryption for libSQL content columns.
+//!
+//! When `LIBSQL_ENCRYPT_CONTENT` is enabled, workspace document bodies,
+//! revisions, trash, chunks, conversation messages and cached LLM responses
+//! are sealed with
+//! AES-256-GCM under a key derived from the secrets master key before they
+//! are written, and opened again on read. Metadata (paths, timestamps, ids)
+//! stays in the clear so queries and indexes keep working.
+//!
+//! Sealed values are `clawyer-at-rest-v1:<salt>:<ciphertext>` (base64). Rows
+//! without the prefix are read as plaintext, so the option can be turned on
+//! for an existing database; [`LibSqlBackend::encrypt_existing_content`]
+//! seals what is already there. Matter files already wrapped in a matter
+//! envelope are stored as-is.
+//!
+//! Keyword search only sees ciphertext for sealed chunks; hybrid search
+//! relies on embeddings for encrypted content.
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use libsql::params;
+use secrecy::SecretString;
+
+use super::{LibSqlBackend, get_text};
+use crate::error::{DatabaseError, WorkspaceError};
+use crate::secrets::SecretsCrypto;
+
+const SEALED_PREFIX: &str = "clawyer-at-rest-v1:";
+
+/// Tables and columns sealed at rest, with each table's key column.
+const SEALED_COLUMNS: &[(&str, &str, &str)] = &[
+    ("memory_documents", "id", "content"),
+    ("memory_document_revisions", "id", "content"),
+    ("memory_document_revisions", "id", "diff"),
+    ("memory_trash", "id", "content"),
+    ("memory_chunks", "id", "content"),
+    ("conversation_messages", "id", "content"),
+    ("llm_response_cache", "cache_key", "response"),
+];
+
+/// Whether a stored value is sealed.
+pub fn is_sealed(stored: &str) -> bool {
+    stored.starts_with(SEALED_PREFIX)
+}
+
+/// Seals and opens column values with the secrets master key.
+pub struct ContentCipher {
+    crypto: Arc<SecretsCrypto>,
+}
+
+impl ContentCipher {
+    pub fn new(crypto: Arc<SecretsCrypto>) -> Self {
+        Self { crypto }
+    }
+
+    pub fn seal(&self, plaintext: &str) -> Result<String, String> {
+        let (ciphertext, salt) = self
+            .crypto
+            .encrypt(plaintext.as_bytes())
+            .map_err(|e| format!("content encrypt failed: {e}"))?;
+        Ok(format!(
+            "{SEALED_PREFIX}{}:{}",
+            BASE64.encode(salt),
+            BASE64.encode(ciphertext)
+        ))
+    }
+
+    pub fn open(&self, stored: &str) -> Result<String, String> {
+        let Some(body) = stored.strip_prefix(SEALED_PREFIX) else {
+            return Ok(stored.to_string());
+        };
+        let (salt, ciphertext) = body
+            .split_once(':')
+            .ok_or_else(|| "malformed sealed content".to_string())?;
+        let salt = BASE64
+            .decode(salt)
+            .map_err(|e| format!("invalid sealed content salt: {e}"))?;
+        let ciphertext = BASE64
+            .decode(ciphertext)
+            .map_err(|e| format!("invalid sealed content ciphertext: {e}"))?;
+        let plaintext = self
+            .crypto
+            .decrypt(&ciphertext, &salt)
+            .map_err(|e| format!("content decrypt failed: {e}"))?;
+        Ok(plaintext.expose().to_string())
+    }
+}
+
+fn ws_err(reason: String) -> WorkspaceError {
+    WorkspaceError::SearchFailed { reason }
+}
+
+impl LibSqlBackend {
+    /// Seal content columns with `crypto` from now on.
+    pub fn with_content_encryption(mut self, crypto: Arc<SecretsCrypto>) -> Self {
+        self.cipher = Some(Arc::new(ContentCipher::new(crypto)));
+        self
+    }
+
+    /// Apply `LIBSQL_ENCRYPT_CONTENT`: when enabled, require the secrets
+    /// master key and seal content with it.
+    pub fn with_content_encryption_from_config(
+        self,
+        enabled: bool,
+        master_key: Option<&SecretString>,
+    ) -> Result<Self, DatabaseError> {
+        if !enabled {
+            return Ok(self);
+        }
+        let master_key = master_key.ok_or_else(|| {
+            DatabaseError::Pool(
+                "LIBSQL_ENCRYPT_CONTENT requires SECRETS_MASTER_KEY (or a keychain master key)"
+                    .to_string(),
+            )
+        })?;
+        let crypto = SecretsCrypto::new(master_key.clone())
+            .map_err(|e| DatabaseError::Pool(format!("content encryption key: {e}")))?;
+        Ok(self.with_content_encryption(Arc::new(crypto)))
+    }
+
+    /// Whether content columns are sealed at rest.
+    pub fn content_encryption_enabled(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    /// Value to store for `plaintext`: sealed when encryption is on (matter
+    /// envelopes and empty strings pass through), unchanged otherwise.
+    pub(crate) fn seal<'a>(&self, plaintext: &'a str) -> Result<Cow<'a, str>, String> {
+        match &self.cipher {
+            Some(cipher)
+                if !plaintext.is_empty()
+                    && !crate::legal::workspace_crypto::is_encrypted_payload(plaintext) =>
+            {
+                cipher.seal(plaintext).map(Cow::Owned)
+            }
+            _ => Ok(Cow::Borrowed(plaintext)),
+        }
+    }
+
+    /// Plaintext for a stored value. Unsealed values pass through; sealed
+    /// values without a configured key are an error rather than ciphertext
+    /// leaking into prompts.
+    pub(crate) fn open(&self, stored: String) -> Result<String, String> {
+        if !is_sealed(&stored) {
+            return Ok(stored);
+        }
+        match &self.cipher {
+            Some(cipher) => cipher.open(&stored),
+            None => Err(
+                "content is encrypted at rest; set LIBSQL_ENCRYPT_CONTENT=true and provide \
+                 SECRETS_MASTER_KEY"
+                    .to_string(),
+            ),
+        }
+    }
+
+    pub(crate) fn seal_ws<'a>(&self, plaintext: &'a str) -> Result<Cow<'a, str>, WorkspaceError> {
+        self.seal(plaintext).map_err(ws_err)
+    }
+
+    pub(crate) fn open_ws(&self, stored: String) -> Result<String, WorkspaceError> {
+        self.open(stored).map_err(ws_err)
+    }
+
+    pub(crate) fn seal_db<'a>(&self, plaintext: &'a str) -> Result<Cow<'a, str>, DatabaseError> {
+        self.seal(plaintext).map_err(DatabaseError::Serialization)
+    }
+
+    pub(crate) fn open_db(&self, stored: String) -> Result<String, DatabaseError> {
+        self.open(stored).map_err(DatabaseError::Serialization)
+    }
+
+    /// Seal every plaintext row in the encrypted columns, then `VACUUM` so
+    /// the plaintext does not linger in free pages. Returns the number of
+    /// values sealed; a no-op when encryption is off or nothing is left.
+    pub async fn encrypt_existing_content(&self) -> Result<usize, DatabaseError> {
+        if self.cipher.is_none() {
+            return Ok(0);
+        }
+        let conn = self.connect().await?;
+        let query_err = |e: libsql::Error| DatabaseError::Query(e.to_string());
+        let mut sealed = 0usize;
+        for (table, key, column) in SEALED_COLUMNS {
+            let mut rows = conn
+                .query(
+                    &format!(
+                        "SELECT {key}, {column} FROM {table} \
+                         WHERE {column} IS NOT NULL AND {column} != '' \
+                           AND substr({column}, 1, ?1) != ?2"
+                    ),
+                    params![SEALED_PREFIX.len() as i64, SEALED_PREFIX],
+                )
+                .await
+                .map_err(query_err)?;
+            let mut pending = Vec::new();
+            while let Some(row) = rows.next().await.map_err(query_err)? {
+                pending.push((get_text(&row, 0), get_text(&row, 1)));
+            }
+            drop(rows);
+            for (id, plaintext) in pending {
+                let stored = self.seal_db(&plaintext)?;
+                if stored == plaintext {
+                    continue;
+                }
+                conn.execute(
+                    &format!("UPDATE {table} SET {column} = ?2 WHERE {key} = ?1"),
+                    params![id, stored.as_ref()],
+                )
+                .await
+                .map_err(query_err)?;
+                sealed += 1;
+            }
+        }
+        if sealed > 0 {
+            conn.execute("VACUUM", ()).await.map_err(query_err)?;
+            tracing::info!(
+                "Encrypted {} existing libSQL content value(s) at rest",
+                sealed
+            );
+        }
+        Ok(sealed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::db::{ConversationStore, Database, WorkspaceStore};
+
+    fn crypto() -> Arc<SecretsCrypto> {
+        Arc::new(
+            SecretsCrypto::new(SecretString::from(
+                "0123456789abcdef0123456789abcdef".to_string(),
+            ))
+            .expect("crypto"),
+        )
+    }
+
+    async fn raw_column(backend: &LibSqlBackend, sql: &str, id: Uuid) -> String {
+        let conn = backend.connect().await.expect("conn");
+        let mut rows = conn
+            .query(sql, params![id.to_string()])
+            .await
+            .expect("query");
+        let row = rows.next().await.expect("row").expect("present");
+        get_text(&row, 0)
+    }
+
+    #[test]
+    fn seal_roundtrip_and_plaintext_passthrough() {
+        let cipher = ContentCipher::new(crypto());
+        let sealed = cipher.seal("privileged memo").expect("seal");
+        assert!(is_sealed(&sealed));
+        assert!(!sealed.contains("privileged"));
+        assert_eq!(cipher.open(&sealed).expect("open"), "privileged memo");
+        assert_eq!(cipher.open("legacy row").expect("open"), "legacy row");
+    }
+
+    #[tokio::test]
+    async fn workspace_and_messages_are_sealed_on_disk() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let backend = LibSqlBackend::new_local(&dir.path().join("clawyer.db"))
+            .await
+            .expect("db")
+            .with_content_encryption(crypto());
+        backend.run_migrations().await.expect("migrations");
+
+        let doc = backend
+            .get_or_create_document_by_path("default", None, "notes/memo.md")
+            .await
+            .expect("doc");
+        backend
+            .update_document(doc.id, "privileged memo", None)
+            .await
+            .expect("write");
+        backend
+            .insert_chunk(doc.id, 0, "privileged memo", None)
+            .await
+            .expect("chunk");
+
+        let stored = raw_column(
+            &backend,
+            "SELECT content FROM memory_documents WHERE id = ?1",
+            doc.id,
+        )
+        .await;
+        assert!(is_sealed(&stored));
+        let read = backend.get_document_by_id(doc.id).await.expect("read back");
+        assert_eq!(read.content, "privileged memo");
+        let revisions = backend
+            .list_document_revisions(doc.id)
+            .await
+            .expect("revisions");
+        assert_eq!(revisions[0].content, "privileged memo");
+
+        let conversation = backend
+            .create_conversation("gateway", "default", None)
+            .await
+            .expect("conversation");
+        let message = backend
+            .add_conversation_message(conversation, "user", "settle for 40k")
+            .await
+            .expect("message");
+        let stored = raw_column(
+            &backend,
+            "SELECT content FROM conversation_messages WHERE id = ?1",
+            message,
+        )
+        .await;
+        assert!(is_sealed(&stored));
+        let messages = backend
+            .list_conversation_messages(conversation)
+            .await
+            .expect("messages");
+        assert_eq!(messages[0].content, "settle for 40k");
+        let summaries = backend
+            .list_conversations_with_preview("default", "gateway", 10)
+            .await
+            .expect("summaries");
+        assert_eq!(summaries[0].title.as_deref(), Some("settle for 40k"));
+    }
+
+    #[tokio::test]
+    async fn cached_llm_responses_are_sealed_on_disk() {
+        use crate::db::LlmResponseCacheStore;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let backend = LibSqlBackend::new_local(&dir.path().join("clawyer.db"))
+            .await
+            .expect("db")
+            .with_content_encryption(crypto());
+        backend.run_migrations().await.expect("migrations");
+
+        let response = serde_json::json!({ "content": "advise settling for 40k" });
+        backend
+            .put_llm_cache_entry(
+                "k1",
+                "model-a",
+                &response,
+                chrono::Utc::now() + chrono::Duration::hours(1),
+            )
+            .await
+            .expect("put");
+        let conn = backend.connect().await.expect("conn");
+        let mut rows = conn
+            .query(
+                "SELECT response FROM llm_response_cache WHERE cache_key = ?1",
+                params!["k1"],
+            )
+            .await
+            .expect("query");
+        let stored = get_text(&rows.next().await.expect("row").expect("present"), 0);
+        assert!(is_sealed(&stored));
+        assert!(!stored.contains("40k"));
+        let hit = backend
+            .get_llm_cache_entry("k1")
+            .await
+            .expect("get")
+            .expect("live entry");
+        assert_eq!(hit.response, response);
+    }
+
+    #[tokio::test]
+    async fn existing_plaintext_is_sealed_and_unreadable_without_key() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("clawyer.db");
+        let plain = LibSqlBackend::new_local(&path).await.expect("db");
+        plain.run_migrations().await.expect("migrations");
+        let doc = plain
+            .get_or_create_document_by_path("default", None, "notes/memo.md")
+            .await
+            .expect("doc");
+        plain
+            .update_document(doc.id, "privileged memo", None)
+            .await
+            .expect("write");
+        drop(plain);
+
+        let encrypted = LibSqlBackend::new_local(&path)
+            .await
+            .expect("reopen")
+            .with_content_encryption(crypto());
+        // Document content plus the first revision's content and diff.
+        assert_eq!(encrypted.encrypt_existing_content().await.expect("seal"), 3);
+        assert_eq!(
+            encrypted.encrypt_existing_content().await.expect("again"),
+            0
+        );
+        let read = encrypted.get_document_by_id(doc.id).await.expect("read");
+        assert_eq!(read.content, "privileged memo");
+        drop(encrypted);
+
+        let keyless = LibSqlBackend::new_local(&path).await.expect("reopen");
+        assert!(keyless.get_document_by_id(doc.id).await.is_err());
+    }
+}
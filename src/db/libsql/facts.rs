@@ -0,0 +1,249 @@
+//! FactStore implementation for LibSqlBackend.
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use libsql::params;
+use uuid::Uuid;
+
+use super::{LibSqlBackend, fmt_ts, get_opt_text, get_text, get_ts, opt_text};
+use crate::db::{CreateMatterFactParams, FactStore, MatterFactQuery, MatterFactRecord};
+use crate::error::DatabaseError;
+
+const MATTER_FACT_COLUMNS: &str = "id, user_id, matter_id, fact_date, description, source_path, \
+     source_locator, source_excerpt, confidence, extractor, created_by, created_at, updated_at";
+
+fn parse_naive_date(raw: &str, field: &str) -> Result<NaiveDate, DatabaseError> {
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d").map_err(|_| {
+        DatabaseError::Serialization(format!("invalid {field} '{raw}' (expected YYYY-MM-DD)"))
+    })
+}
+
+fn row_to_matter_fact(row: &libsql::Row) -> Result<MatterFactRecord, DatabaseError> {
+    let id_raw = get_text(row, 0);
+    let id = id_raw
+        .parse::<Uuid>()
+        .map_err(|e| DatabaseError::Serialization(format!("invalid fact id '{id_raw}': {e}")))?;
+    Ok(MatterFactRecord {
+        id,
+        user_id: get_text(row, 1),
+        matter_id: get_text(row, 2),
+        fact_date: parse_naive_date(&get_text(row, 3), "fact_date")?,
+        description: get_text(row, 4),
+        source_path: get_text(row, 5),
+        source_locator: get_opt_text(row, 6),
+        source_excerpt: get_opt_text(row, 7),
+        confidence: row.get::<f64>(8).unwrap_or_default(),
+        extractor: get_text(row, 9),
+        created_by: get_text(row, 10),
+        created_at: get_ts(row, 11),
+        updated_at: get_ts(row, 12),
+    })
+}
+
+#[async_trait]
+impl FactStore for LibSqlBackend {
+    async fn create_matter_fact(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        params: &CreateMatterFactParams,
+    ) -> Result<MatterFactRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        let id = Uuid::new_v4().to_string();
+        let now = fmt_ts(&Utc::now());
+        conn.execute(
+            "INSERT INTO matter_facts \
+             (id, user_id, matter_id, fact_date, description, source_path, source_locator, \
+              source_excerpt, confidence, extractor, created_by, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?12)",
+            params![
+                id.as_str(),
+                user_id,
+                matter_id,
+                params.fact_date.format("%Y-%m-%d").to_string(),
+                params.description.as_str(),
+                params.source_path.as_str(),
+                opt_text(params.source_locator.as_deref()),
+                opt_text(params.source_excerpt.as_deref()),
+                params.confidence,
+                params.extractor.as_str(),
+                params.created_by.as_str(),
+                now,
+            ],
+        )
+        .await?;
+        let row = conn
+            .query(
+                &format!("SELECT {MATTER_FACT_COLUMNS} FROM matter_facts WHERE id = ?1"),
+                params![id.as_str()],
+            )
+            .await?
+            .next()
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound {
+                entity: "matter_fact".to_string(),
+                id,
+            })?;
+        row_to_matter_fact(&row)
+    }
+
+    async fn list_matter_facts(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        query: &MatterFactQuery,
+    ) -> Result<Vec<MatterFactRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {MATTER_FACT_COLUMNS} FROM matter_facts \
+                     WHERE user_id = ?1 AND matter_id = ?2 \
+                       AND (?3 IS NULL OR fact_date >= ?3) \
+                       AND (?4 IS NULL OR fact_date <= ?4) \
+                       AND (?5 IS NULL OR source_path = ?5) \
+                       AND (?6 IS NULL OR confidence >= ?6) \
+                     ORDER BY fact_date ASC, created_at ASC, rowid ASC"
+                ),
+                params![
+                    user_id,
+                    matter_id,
+                    query.from.map(|d| d.format("%Y-%m-%d").to_string()),
+                    query.to.map(|d| d.format("%Y-%m-%d").to_string()),
+                    opt_text(query.source_path.as_deref()),
+                    query.min_confidence,
+                ],
+            )
+            .await?;
+        let mut records = Vec::new();
+        while let Some(row) = rows.next().await? {
+            records.push(row_to_matter_fact(&row)?);
+        }
+        Ok(records)
+    }
+
+    async fn delete_matter_facts_for_source(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        source_path: &str,
+    ) -> Result<u64, DatabaseError> {
+        let conn = self.connect().await?;
+        let deleted = conn
+            .execute(
+                "DELETE FROM matter_facts \
+                 WHERE user_id = ?1 AND matter_id = ?2 AND source_path = ?3",
+                params![user_id, matter_id, source_path],
+            )
+            .await?;
+        Ok(deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::db::{CreateMatterFactParams, MatterFactQuery};
+
+    fn fact(
+        date: &str,
+        description: &str,
+        source: &str,
+        confidence: f64,
+    ) -> CreateMatterFactParams {
+        CreateMatterFactParams {
+            fact_date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            description: description.to_string(),
+            source_path: source.to_string(),
+            source_locator: Some("line 1".to_string()),
+            source_excerpt: None,
+            confidence,
+            extractor: "heuristic".to_string(),
+            created_by: "default".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn facts_list_in_date_order_with_filters() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let letter = "matters/acme/correspondence/letter.md";
+        let contract = "matters/acme/contracts/msa.md";
+
+        db.create_matter_fact(
+            "default",
+            "acme",
+            &fact("2024-05-01", "Demand sent", letter, 0.6),
+        )
+        .await
+        .unwrap();
+        db.create_matter_fact(
+            "default",
+            "acme",
+            &fact("2023-01-15", "MSA signed", contract, 0.9),
+        )
+        .await
+        .unwrap();
+        db.create_matter_fact(
+            "default",
+            "other",
+            &fact("2024-01-01", "Unrelated", letter, 0.9),
+        )
+        .await
+        .unwrap();
+
+        let all = db
+            .list_matter_facts("default", "acme", &MatterFactQuery::default())
+            .await
+            .unwrap();
+        let descriptions: Vec<_> = all.iter().map(|f| f.description.as_str()).collect();
+        assert_eq!(descriptions, vec!["MSA signed", "Demand sent"]);
+        assert_eq!(all[0].source_locator.as_deref(), Some("line 1"));
+
+        let since_2024 = db
+            .list_matter_facts(
+                "default",
+                "acme",
+                &MatterFactQuery {
+                    from: NaiveDate::from_ymd_opt(2024, 1, 1),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(since_2024.len(), 1);
+        assert_eq!(since_2024[0].description, "Demand sent");
+
+        let confident = db
+            .list_matter_facts(
+                "default",
+                "acme",
+                &MatterFactQuery {
+                    min_confidence: Some(0.8),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(confident.len(), 1);
+        assert_eq!(confident[0].source_path, contract);
+
+        let deleted = db
+            .delete_matter_facts_for_source("default", "acme", letter)
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+        let remaining = db
+            .list_matter_facts("default", "acme", &MatterFactQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(
+            db.list_matter_facts("default", "other", &MatterFactQuery::default())
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
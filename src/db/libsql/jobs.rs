@@ -10,7 +10,7 @@ use super::{
     get_opt_text, get_opt_ts, get_text, get_ts, opt_text, opt_text_owned, parse_job_state,
 };
 use crate::context::{ActionRecord, JobContext, JobState};
-use crate::db::JobStore;
+use crate::db::{EstimationSampleRecord, JobStore};
 use crate::error::DatabaseError;
 use crate::history::LlmCallRecord;
 
@@ -327,4 +327,44 @@ impl JobStore for LibSqlBackend {
             .map_err(|e| DatabaseError::Query(e.to_string()))?;
         Ok(())
     }
+
+    async fn list_estimation_samples(
+        &self,
+        category: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<EstimationSampleRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                r#"
+                SELECT category, estimated_cost, actual_cost, estimated_time_secs,
+                       actual_time_secs, created_at
+                FROM estimation_snapshots
+                WHERE actual_cost IS NOT NULL AND actual_time_secs IS NOT NULL
+                  AND (?1 IS NULL OR category = ?1)
+                ORDER BY created_at DESC
+                LIMIT ?2
+                "#,
+                params![opt_text(category), limit],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+
+        let mut samples = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            samples.push(EstimationSampleRecord {
+                category: get_text(&row, 0),
+                estimated_cost: get_decimal(&row, 1),
+                actual_cost: get_decimal(&row, 2),
+                estimated_time_secs: get_i64(&row, 3) as i32,
+                actual_time_secs: get_i64(&row, 4) as i32,
+                created_at: get_ts(&row, 5),
+            });
+        }
+        Ok(samples)
+    }
 }
@@ -7,13 +7,15 @@ use uuid::Uuid;
 use crate::db::{
     ConflictClearanceInfo, ConflictClearanceRecord, ConflictClearanceStatus, ConflictDecision,
     ConflictHit, CreatePartyRelationshipParams, LegalConflictStore, MatterPartyRecord,
-    PartyRelationshipRecord, PartyRole, UpsertMatterPartyParams, conflict_terms_from_text,
-    normalize_party_name, trigram_similarity,
+    PartyCandidateRecord, PartyCandidateStatus, PartyEntityType, PartyRelationshipRecord,
+    PartyRole, ReviewPartyCandidateParams, UpsertMatterPartyParams, UpsertPartyCandidateParams,
+    conflict_terms_from_text, normalize_party_name, trigram_similarity,
 };
 use crate::error::DatabaseError;
 
 use super::{
-    LibSqlBackend, get_i64, get_opt_text, get_text, opt_text, opt_text_owned, parse_timestamp,
+    LibSqlBackend, get_i64, get_opt_text, get_opt_ts, get_text, get_ts, opt_text, opt_text_owned,
+    parse_timestamp,
 };
 
 fn match_priority(matched_via: &str) -> u8 {
@@ -351,6 +353,57 @@ async fn relationship_record_by_id(
     })
 }
 
+const PARTY_CANDIDATE_COLUMNS: &str = "id, matter_id, name, entity_type, source_path, snippet, \
+     confidence, extractor, status, party_id, reviewed_by, reviewed_at, created_at, updated_at";
+
+fn row_to_party_candidate(row: &libsql::Row) -> Result<PartyCandidateRecord, DatabaseError> {
+    let entity_type_raw = get_text(row, 3);
+    let entity_type = PartyEntityType::from_db_value(&entity_type_raw).ok_or_else(|| {
+        DatabaseError::Serialization(format!("invalid entity type '{entity_type_raw}'"))
+    })?;
+    let status_raw = get_text(row, 8);
+    let status = PartyCandidateStatus::from_db_value(&status_raw).ok_or_else(|| {
+        DatabaseError::Serialization(format!("invalid party candidate status '{status_raw}'"))
+    })?;
+    Ok(PartyCandidateRecord {
+        id: parse_uuid(&get_text(row, 0), "party_candidates.id")?,
+        matter_id: get_text(row, 1),
+        name: get_text(row, 2),
+        entity_type,
+        source_path: get_text(row, 4),
+        snippet: get_opt_text(row, 5),
+        confidence: row.get::<f64>(6).unwrap_or_default(),
+        extractor: get_text(row, 7),
+        status,
+        party_id: get_opt_text(row, 9)
+            .map(|raw| parse_uuid(&raw, "party_candidates.party_id"))
+            .transpose()?,
+        reviewed_by: get_opt_text(row, 10),
+        reviewed_at: get_opt_ts(row, 11),
+        created_at: get_ts(row, 12),
+        updated_at: get_ts(row, 13),
+    })
+}
+
+async fn party_candidate_by_id_with_conn(
+    conn: &libsql::Connection,
+    matter_id: &str,
+    candidate_id: &str,
+) -> Result<Option<PartyCandidateRecord>, DatabaseError> {
+    let row = conn
+        .query(
+            &format!(
+                "SELECT {PARTY_CANDIDATE_COLUMNS} FROM party_candidates \
+                 WHERE matter_id = ?1 AND id = ?2 LIMIT 1"
+            ),
+            params![matter_id, candidate_id],
+        )
+        .await?
+        .next()
+        .await?;
+    row.as_ref().map(row_to_party_candidate).transpose()
+}
+
 #[async_trait::async_trait]
 impl LegalConflictStore for LibSqlBackend {
     async fn find_conflict_hits_for_names(
@@ -1013,6 +1066,120 @@ impl LegalConflictStore for LibSqlBackend {
             }
         }
     }
+    async fn upsert_party_candidate(
+        &self,
+        matter_id: &str,
+        input: &UpsertPartyCandidateParams,
+    ) -> Result<PartyCandidateRecord, DatabaseError> {
+        let name = input.name.trim();
+        let normalized = normalize_party_name(name);
+        if normalized.is_empty() {
+            return Err(DatabaseError::Serialization(
+                "party candidate name cannot be empty".to_string(),
+            ));
+        }
+        let conn = self.connect().await?;
+        conn.execute(
+            "INSERT INTO party_candidates \
+             (id, matter_id, name, name_normalized, entity_type, source_path, snippet, confidence, \
+              extractor, status, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 'pending', datetime('now'), datetime('now')) \
+             ON CONFLICT(matter_id, name_normalized, source_path) DO UPDATE SET \
+               snippet = COALESCE(excluded.snippet, party_candidates.snippet), \
+               confidence = MAX(excluded.confidence, party_candidates.confidence), \
+               updated_at = datetime('now') \
+             WHERE party_candidates.status = 'pending'",
+            params![
+                Uuid::new_v4().to_string(),
+                matter_id,
+                name,
+                normalized.as_str(),
+                input.entity_type.as_str(),
+                input.source_path.as_str(),
+                opt_text(input.snippet.as_deref()),
+                input.confidence,
+                input.extractor.as_str(),
+            ],
+        )
+        .await?;
+        let row = conn
+            .query(
+                &format!(
+                    "SELECT {PARTY_CANDIDATE_COLUMNS} FROM party_candidates \
+                     WHERE matter_id = ?1 AND name_normalized = ?2 AND source_path = ?3 LIMIT 1"
+                ),
+                params![matter_id, normalized.as_str(), input.source_path.as_str()],
+            )
+            .await?
+            .next()
+            .await?
+            .ok_or_else(|| DatabaseError::Query("failed to resolve party candidate".to_string()))?;
+        row_to_party_candidate(&row)
+    }
+
+    async fn list_party_candidates(
+        &self,
+        matter_id: &str,
+        status: Option<PartyCandidateStatus>,
+    ) -> Result<Vec<PartyCandidateRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {PARTY_CANDIDATE_COLUMNS} FROM party_candidates \
+                     WHERE matter_id = ?1 AND (?2 IS NULL OR status = ?2) \
+                     ORDER BY created_at DESC, name_normalized ASC"
+                ),
+                params![
+                    matter_id,
+                    opt_text(status.map(PartyCandidateStatus::as_str))
+                ],
+            )
+            .await?;
+        let mut out = Vec::new();
+        while let Some(row) = rows.next().await? {
+            out.push(row_to_party_candidate(&row)?);
+        }
+        Ok(out)
+    }
+
+    async fn get_party_candidate(
+        &self,
+        matter_id: &str,
+        candidate_id: Uuid,
+    ) -> Result<Option<PartyCandidateRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        party_candidate_by_id_with_conn(&conn, matter_id, &candidate_id.to_string()).await
+    }
+
+    async fn review_party_candidate(
+        &self,
+        matter_id: &str,
+        candidate_id: Uuid,
+        input: &ReviewPartyCandidateParams,
+    ) -> Result<Option<PartyCandidateRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let candidate_id = candidate_id.to_string();
+        let updated = conn
+            .execute(
+                "UPDATE party_candidates SET \
+                   status = ?3, party_id = ?4, reviewed_by = ?5, \
+                   reviewed_at = datetime('now'), updated_at = datetime('now') \
+                 WHERE matter_id = ?1 AND id = ?2 AND status = 'pending'",
+                params![
+                    matter_id,
+                    candidate_id.as_str(),
+                    input.status.as_str(),
+                    opt_text_owned(input.party_id.map(|id| id.to_string())),
+                    input.reviewed_by.as_str(),
+                ],
+            )
+            .await?;
+        if updated == 0 {
+            return Ok(None);
+        }
+        party_candidate_by_id_with_conn(&conn, matter_id, &candidate_id).await
+    }
 }
 
 #[cfg(test)]
@@ -1362,6 +1529,101 @@ mod tests {
         assert_eq!(alias_count, 2);
     }
 
+    #[tokio::test]
+    async fn party_candidates_dedupe_and_stay_reviewed() {
+        let fixture = setup_backend().await;
+        let params = UpsertPartyCandidateParams {
+            name: "Globex, LLC".to_string(),
+            entity_type: PartyEntityType::Organization,
+            source_path: "matters/demo/contracts/msa.md".to_string(),
+            snippet: Some("between Acme and Globex, LLC".to_string()),
+            confidence: 0.6,
+            extractor: "heuristic".to_string(),
+        };
+        let first = fixture
+            .backend
+            .upsert_party_candidate("demo", &params)
+            .await
+            .expect("first upsert");
+        let again = fixture
+            .backend
+            .upsert_party_candidate(
+                "demo",
+                &UpsertPartyCandidateParams {
+                    confidence: 0.9,
+                    ..params.clone()
+                },
+            )
+            .await
+            .expect("second upsert");
+        assert_eq!(first.id, again.id);
+        assert_eq!(again.confidence, 0.9);
+        assert_eq!(table_count(&fixture.backend, "party_candidates").await, 1);
+
+        let rejected = fixture
+            .backend
+            .review_party_candidate(
+                "demo",
+                first.id,
+                &ReviewPartyCandidateParams {
+                    status: PartyCandidateStatus::Rejected,
+                    reviewed_by: "reviewer".to_string(),
+                    party_id: None,
+                },
+            )
+            .await
+            .expect("review")
+            .expect("candidate exists");
+        assert_eq!(rejected.status, PartyCandidateStatus::Rejected);
+        assert!(rejected.reviewed_at.is_some());
+        // A reviewed candidate cannot be reviewed again.
+        assert!(
+            fixture
+                .backend
+                .review_party_candidate(
+                    "demo",
+                    first.id,
+                    &ReviewPartyCandidateParams {
+                        status: PartyCandidateStatus::Accepted,
+                        reviewed_by: "second-reviewer".to_string(),
+                        party_id: None,
+                    },
+                )
+                .await
+                .expect("second review")
+                .is_none()
+        );
+
+        // Re-ingesting the same document must not reopen the decision.
+        let reingested = fixture
+            .backend
+            .upsert_party_candidate("demo", &params)
+            .await
+            .expect("re-ingest");
+        assert_eq!(reingested.status, PartyCandidateStatus::Rejected);
+
+        let pending = fixture
+            .backend
+            .list_party_candidates("demo", Some(PartyCandidateStatus::Pending))
+            .await
+            .expect("list pending");
+        assert!(pending.is_empty());
+        let all = fixture
+            .backend
+            .list_party_candidates("demo", None)
+            .await
+            .expect("list all");
+        assert_eq!(all.len(), 1);
+        assert!(
+            fixture
+                .backend
+                .get_party_candidate("other", first.id)
+                .await
+                .expect("get")
+                .is_none()
+        );
+    }
+
     #[tokio::test]
     async fn reset_conflict_graph_clears_party_graph_tables() {
         let fixture = setup_backend().await;
@@ -6,13 +6,16 @@ use uuid::Uuid;
 use crate::db::{
     BillingRateScheduleRecord, BillingRateStore, CitationVerificationResultRecord,
     CitationVerificationRunRecord, CitationVerificationStatus, CitationVerificationStore,
+    ClientStatusReportRecord, ClientStatusReportStatus, ClientStatusReportStore,
     ComputeTrustReconciliationParams, CreateBillingRateScheduleParams,
     CreateCitationVerificationResultParams, CreateCitationVerificationRunParams,
     CreateTrustStatementImportParams, CreateTrustStatementLineParams, DocumentReadinessState,
-    MatterDocumentRecord, MatterDocumentStore, TrustAccountRecord, TrustAccountingStore,
-    TrustLedgerEntryRecord, TrustReconciliationRecord, TrustReconciliationStatus,
-    TrustStatementImportRecord, TrustStatementLineRecord, UpdateBillingRateScheduleParams,
-    UpsertTrustAccountParams,
+    DocumentTranslationRecord, DocumentTranslationStore, MatterDocumentRecord, MatterDocumentStore,
+    TranslationStatus, TrustAccountRecord, TrustAccountingStore, TrustLedgerEntryRecord,
+    TrustReconciliationRecord, TrustReconciliationStatus, TrustStatementImportRecord,
+    TrustStatementLineRecord, UpdateBillingRateScheduleParams, UpdateClientStatusReportParams,
+    UpdateDocumentTranslationStatusParams, UpsertClientStatusReportParams,
+    UpsertDocumentTranslationParams, UpsertTrustAccountParams,
 };
 use crate::error::DatabaseError;
 
@@ -109,6 +112,73 @@ fn row_to_citation_result(
     })
 }
 
+const DOCUMENT_TRANSLATION_COLUMNS: &str = "id, user_id, matter_id, source_path, source_language, target_language, translation_path, provider, status, translator, certified_path, certified_at, created_by, created_at, updated_at";
+
+fn row_to_document_translation(
+    row: &libsql::Row,
+) -> Result<DocumentTranslationRecord, DatabaseError> {
+    let status_raw = get_text(row, 8);
+    Ok(DocumentTranslationRecord {
+        id: parse_uuid(&get_text(row, 0), "document_translations.id")?,
+        user_id: get_text(row, 1),
+        matter_id: get_text(row, 2),
+        source_path: get_text(row, 3),
+        source_language: get_opt_text(row, 4),
+        target_language: get_text(row, 5),
+        translation_path: get_text(row, 6),
+        provider: get_text(row, 7),
+        status: TranslationStatus::from_db_value(&status_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("invalid translation status '{status_raw}'"))
+        })?,
+        translator: get_opt_text(row, 9),
+        certified_path: get_opt_text(row, 10),
+        certified_at: get_opt_text(row, 11)
+            .map(|value| parse_timestamp(&value))
+            .transpose()
+            .map_err(|e| DatabaseError::Serialization(e.to_string()))?,
+        created_by: get_text(row, 12),
+        created_at: parse_timestamp(&get_text(row, 13))
+            .map_err(|e| DatabaseError::Serialization(e.to_string()))?,
+        updated_at: parse_timestamp(&get_text(row, 14))
+            .map_err(|e| DatabaseError::Serialization(e.to_string()))?,
+    })
+}
+
+const CLIENT_STATUS_REPORT_COLUMNS: &str = "id, user_id, matter_id, report_path, subject, recipient, status, reviewed_by, review_note, reviewed_at, sent_at, delivery_error, created_by, created_at, updated_at";
+
+fn row_to_client_status_report(
+    row: &libsql::Row,
+) -> Result<ClientStatusReportRecord, DatabaseError> {
+    let status_raw = get_text(row, 6);
+    let opt_ts = |idx: i32| {
+        get_opt_text(row, idx)
+            .map(|value| parse_timestamp(&value))
+            .transpose()
+            .map_err(|e| DatabaseError::Serialization(e.to_string()))
+    };
+    Ok(ClientStatusReportRecord {
+        id: parse_uuid(&get_text(row, 0), "client_status_reports.id")?,
+        user_id: get_text(row, 1),
+        matter_id: get_text(row, 2),
+        report_path: get_text(row, 3),
+        subject: get_text(row, 4),
+        recipient: get_opt_text(row, 5),
+        status: ClientStatusReportStatus::from_db_value(&status_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("invalid status report status '{status_raw}'"))
+        })?,
+        reviewed_by: get_opt_text(row, 7),
+        review_note: get_opt_text(row, 8),
+        reviewed_at: opt_ts(9)?,
+        sent_at: opt_ts(10)?,
+        delivery_error: get_opt_text(row, 11),
+        created_by: get_text(row, 12),
+        created_at: parse_timestamp(&get_text(row, 13))
+            .map_err(|e| DatabaseError::Serialization(e.to_string()))?,
+        updated_at: parse_timestamp(&get_text(row, 14))
+            .map_err(|e| DatabaseError::Serialization(e.to_string()))?,
+    })
+}
+
 fn row_to_trust_account(row: &libsql::Row) -> Result<TrustAccountRecord, DatabaseError> {
     Ok(TrustAccountRecord {
         id: parse_uuid(&get_text(row, 0), "trust_accounts.id")?,
@@ -556,6 +626,271 @@ impl CitationVerificationStore for LibSqlBackend {
     }
 }
 
+#[async_trait::async_trait]
+impl DocumentTranslationStore for LibSqlBackend {
+    async fn upsert_document_translation(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        input: &UpsertDocumentTranslationParams,
+    ) -> Result<DocumentTranslationRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute(
+            "INSERT INTO document_translations \
+             (id, user_id, matter_id, source_path, source_language, target_language, translation_path, provider, created_by, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, datetime('now'), datetime('now')) \
+             ON CONFLICT (user_id, matter_id, source_path, target_language) DO UPDATE SET \
+               source_language = excluded.source_language, \
+               translation_path = excluded.translation_path, \
+               provider = excluded.provider, \
+               updated_at = datetime('now')",
+            params![
+                Uuid::new_v4().to_string(),
+                user_id,
+                matter_id,
+                input.source_path.as_str(),
+                opt_text(input.source_language.as_deref()),
+                input.target_language.as_str(),
+                input.translation_path.as_str(),
+                input.provider.as_str(),
+                input.created_by.as_str(),
+            ],
+        )
+        .await?;
+        let row = conn
+            .query(
+                &format!(
+                    "SELECT {DOCUMENT_TRANSLATION_COLUMNS} FROM document_translations \
+                     WHERE user_id = ?1 AND matter_id = ?2 AND source_path = ?3 AND target_language = ?4 LIMIT 1"
+                ),
+                params![
+                    user_id,
+                    matter_id,
+                    input.source_path.as_str(),
+                    input.target_language.as_str(),
+                ],
+            )
+            .await?
+            .next()
+            .await?
+            .ok_or_else(|| DatabaseError::Query("failed to load document translation".to_string()))?;
+        row_to_document_translation(&row)
+    }
+
+    async fn list_document_translations(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<Vec<DocumentTranslationRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {DOCUMENT_TRANSLATION_COLUMNS} FROM document_translations \
+                     WHERE user_id = ?1 AND matter_id = ?2 \
+                     ORDER BY updated_at DESC, source_path ASC"
+                ),
+                params![user_id, matter_id],
+            )
+            .await?;
+        let mut out = Vec::new();
+        while let Some(row) = rows.next().await? {
+            out.push(row_to_document_translation(&row)?);
+        }
+        Ok(out)
+    }
+
+    async fn get_document_translation(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        translation_id: Uuid,
+    ) -> Result<Option<DocumentTranslationRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let row = conn
+            .query(
+                &format!(
+                    "SELECT {DOCUMENT_TRANSLATION_COLUMNS} FROM document_translations \
+                     WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 LIMIT 1"
+                ),
+                params![user_id, matter_id, translation_id.to_string()],
+            )
+            .await?
+            .next()
+            .await?;
+        row.map(|row| row_to_document_translation(&row)).transpose()
+    }
+
+    async fn update_document_translation_status(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        translation_id: Uuid,
+        input: &UpdateDocumentTranslationStatusParams,
+    ) -> Result<Option<DocumentTranslationRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let certified = input.status == TranslationStatus::Certified;
+        conn.execute(
+            "UPDATE document_translations SET \
+               status = ?4, \
+               translator = COALESCE(?5, translator), \
+               certified_path = CASE WHEN ?7 THEN ?6 ELSE NULL END, \
+               certified_at = CASE WHEN ?7 THEN datetime('now') ELSE NULL END, \
+               updated_at = datetime('now') \
+             WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3",
+            params![
+                user_id,
+                matter_id,
+                translation_id.to_string(),
+                input.status.as_str(),
+                opt_text(input.translator.as_deref()),
+                opt_text(input.certified_path.as_deref()),
+                certified,
+            ],
+        )
+        .await?;
+        self.get_document_translation(user_id, matter_id, translation_id)
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl ClientStatusReportStore for LibSqlBackend {
+    async fn upsert_client_status_report(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        input: &UpsertClientStatusReportParams,
+    ) -> Result<ClientStatusReportRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute(
+            "INSERT INTO client_status_reports \
+             (id, user_id, matter_id, report_path, subject, recipient, created_by, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, datetime('now'), datetime('now')) \
+             ON CONFLICT (user_id, matter_id, report_path) DO UPDATE SET \
+               subject = excluded.subject, \
+               recipient = excluded.recipient, \
+               status = 'pending_approval', \
+               reviewed_by = NULL, \
+               review_note = NULL, \
+               reviewed_at = NULL, \
+               delivery_error = NULL, \
+               updated_at = datetime('now')",
+            params![
+                Uuid::new_v4().to_string(),
+                user_id,
+                matter_id,
+                input.report_path.as_str(),
+                input.subject.as_str(),
+                opt_text(input.recipient.as_deref()),
+                input.created_by.as_str(),
+            ],
+        )
+        .await?;
+        let row = conn
+            .query(
+                &format!(
+                    "SELECT {CLIENT_STATUS_REPORT_COLUMNS} FROM client_status_reports \
+                     WHERE user_id = ?1 AND matter_id = ?2 AND report_path = ?3 LIMIT 1"
+                ),
+                params![user_id, matter_id, input.report_path.as_str()],
+            )
+            .await?
+            .next()
+            .await?
+            .ok_or_else(|| {
+                DatabaseError::Query("failed to load client status report".to_string())
+            })?;
+        row_to_client_status_report(&row)
+    }
+
+    async fn list_client_status_reports(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<Vec<ClientStatusReportRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {CLIENT_STATUS_REPORT_COLUMNS} FROM client_status_reports \
+                     WHERE user_id = ?1 AND matter_id = ?2 \
+                     ORDER BY created_at DESC, report_path DESC"
+                ),
+                params![user_id, matter_id],
+            )
+            .await?;
+        let mut out = Vec::new();
+        while let Some(row) = rows.next().await? {
+            out.push(row_to_client_status_report(&row)?);
+        }
+        Ok(out)
+    }
+
+    async fn get_client_status_report(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        report_id: Uuid,
+    ) -> Result<Option<ClientStatusReportRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let row = conn
+            .query(
+                &format!(
+                    "SELECT {CLIENT_STATUS_REPORT_COLUMNS} FROM client_status_reports \
+                     WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 LIMIT 1"
+                ),
+                params![user_id, matter_id, report_id.to_string()],
+            )
+            .await?
+            .next()
+            .await?;
+        row.map(|row| row_to_client_status_report(&row)).transpose()
+    }
+
+    async fn update_client_status_report(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        report_id: Uuid,
+        input: &UpdateClientStatusReportParams,
+    ) -> Result<Option<ClientStatusReportRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let reviewed = matches!(
+            input.status,
+            ClientStatusReportStatus::Approved | ClientStatusReportStatus::Rejected
+        );
+        let sent = input.status == ClientStatusReportStatus::Sent;
+        conn.execute(
+            "UPDATE client_status_reports SET \
+               status = ?4, \
+               reviewed_by = COALESCE(?5, reviewed_by), \
+               review_note = COALESCE(?6, review_note), \
+               recipient = COALESCE(?7, recipient), \
+               delivery_error = ?8, \
+               reviewed_at = CASE WHEN ?9 THEN datetime('now') ELSE reviewed_at END, \
+               sent_at = CASE WHEN ?10 THEN datetime('now') ELSE sent_at END, \
+               updated_at = datetime('now') \
+             WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3",
+            params![
+                user_id,
+                matter_id,
+                report_id.to_string(),
+                input.status.as_str(),
+                opt_text(input.reviewed_by.as_deref()),
+                opt_text(input.review_note.as_deref()),
+                opt_text(input.recipient.as_deref()),
+                opt_text(input.delivery_error.as_deref()),
+                reviewed,
+                sent,
+            ],
+        )
+        .await?;
+        self.get_client_status_report(user_id, matter_id, report_id)
+            .await
+    }
+}
+
 #[async_trait::async_trait]
 impl TrustAccountingStore for LibSqlBackend {
     async fn get_primary_trust_account(
@@ -13,11 +13,12 @@ use crate::db::{
     CreateMatterTaskParams, CreateTimeEntryParams, CreateTrustLedgerEntryParams,
     DeadlineOverrideAuditRecord, DocumentReadinessState, DocumentTemplateRecord,
     DocumentTemplateStore, DocumentVersionRecord, DocumentVersionStore, ExpenseCategory,
-    ExpenseEntryRecord, InvoiceLineItemRecord, InvoiceRecord, InvoiceStatus, LegalRestoreStore,
-    MatterDeadlineRecord, MatterDeadlineStore, MatterDeadlineType, MatterDocumentCategory,
-    MatterDocumentRecord, MatterDocumentStore, MatterMemberRole, MatterMembershipRecord,
-    MatterNoteRecord, MatterNoteStore, MatterRecord, MatterStatus, MatterStore, MatterTaskRecord,
-    MatterTaskStatus, MatterTaskStore, MatterTimeSummary, OverrideDeadlineParams, RbacStore,
+    ExpenseEntryRecord, HistoryCounts, InvoiceLineItemRecord, InvoiceRecord, InvoiceStatus,
+    LegalRestoreStore, MatterDeadlineRecord, MatterDeadlineStore, MatterDeadlineType,
+    MatterDocumentCategory, MatterDocumentRecord, MatterDocumentStore, MatterMemberRole,
+    MatterMembershipRecord, MatterNoteRecord, MatterNoteStore, MatterRecord, MatterStatus,
+    MatterStore, MatterTaskChecklistItem, MatterTaskMove, MatterTaskRecord, MatterTaskStatus,
+    MatterTaskStore, MatterTimeSummary, OrgRole, OverrideDeadlineParams, RbacStore,
     RecordInvoicePaymentParams, RecordInvoicePaymentResult, TimeEntryRecord, TimeExpenseStore,
     TrustAccountingStore, TrustLedgerEntryRecord, TrustLedgerEntryType, TrustLedgerSource,
     UpdateClientParams, UpdateDocumentTemplateParams, UpdateExpenseEntryParams,
@@ -182,7 +183,7 @@ fn parse_json_array_i32(raw: &str) -> Result<Vec<i32>, DatabaseError> {
         .unwrap_or_default())
 }
 
-fn row_to_client_record(row: &libsql::Row) -> Result<ClientRecord, DatabaseError> {
+pub(super) fn row_to_client_record(row: &libsql::Row) -> Result<ClientRecord, DatabaseError> {
     let client_type_raw = get_text(row, 4);
     Ok(ClientRecord {
         id: parse_uuid(&get_text(row, 0), "client.id")?,
@@ -225,6 +226,76 @@ fn row_to_matter_record(row: &libsql::Row) -> Result<MatterRecord, DatabaseError
     })
 }
 
+const MATTER_TASK_COLUMNS: &str = "id, user_id, matter_id, title, description, status, assignee, due_at, \
+     blocked_by, created_at, updated_at, position, parent_task_id, checklist";
+
+fn parse_task_checklist(raw: &str) -> Result<Vec<MatterTaskChecklistItem>, DatabaseError> {
+    if raw.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(raw).map_err(|e| DatabaseError::Serialization(e.to_string()))
+}
+
+fn task_checklist_json(items: &[MatterTaskChecklistItem]) -> Result<String, DatabaseError> {
+    serde_json::to_string(items).map_err(|e| DatabaseError::Serialization(e.to_string()))
+}
+
+/// Mark `parent_id` done once all of its subtasks are done or cancelled (at
+/// least one done), then repeat for its own parent.
+async fn roll_up_matter_task_parents(
+    conn: &libsql::Connection,
+    user_id: &str,
+    matter_id: &str,
+    mut parent_id: Option<Uuid>,
+) -> Result<(), DatabaseError> {
+    let mut seen = HashSet::new();
+    while let Some(id) = parent_id.take().filter(|id| seen.insert(*id)) {
+        let completed = conn
+            .execute(
+                "UPDATE matter_tasks SET \
+                    status = 'done', \
+                    position = (SELECT COALESCE(MAX(position), -1) + 1 FROM matter_tasks \
+                                WHERE user_id = ?1 AND matter_id = ?2 AND status = 'done'), \
+                    updated_at = datetime('now') \
+                 WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 \
+                   AND status NOT IN ('done', 'cancelled') \
+                   AND EXISTS (SELECT 1 FROM matter_tasks \
+                               WHERE user_id = ?1 AND matter_id = ?2 AND parent_task_id = ?3 \
+                                 AND status = 'done') \
+                   AND NOT EXISTS (SELECT 1 FROM matter_tasks \
+                                   WHERE user_id = ?1 AND matter_id = ?2 AND parent_task_id = ?3 \
+                                     AND status NOT IN ('done', 'cancelled'))",
+                params![user_id, matter_id, id.to_string()],
+            )
+            .await?;
+        if completed == 0 {
+            break;
+        }
+        parent_id = matter_task_parent_id(conn, user_id, matter_id, id).await?;
+    }
+    Ok(())
+}
+
+async fn matter_task_parent_id(
+    conn: &libsql::Connection,
+    user_id: &str,
+    matter_id: &str,
+    task_id: Uuid,
+) -> Result<Option<Uuid>, DatabaseError> {
+    let row = conn
+        .query(
+            "SELECT parent_task_id FROM matter_tasks \
+             WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3",
+            params![user_id, matter_id, task_id.to_string()],
+        )
+        .await?
+        .next()
+        .await?;
+    row.and_then(|row| get_opt_text(&row, 0))
+        .map(|raw| parse_uuid(&raw, "matter_tasks.parent_task_id"))
+        .transpose()
+}
+
 fn row_to_matter_task_record(row: &libsql::Row) -> Result<MatterTaskRecord, DatabaseError> {
     let status_raw = get_text(row, 5);
     let due_at = parse_dt_opt(get_opt_text(row, 7))?;
@@ -238,6 +309,11 @@ fn row_to_matter_task_record(row: &libsql::Row) -> Result<MatterTaskRecord, Data
         assignee: get_opt_text(row, 6),
         due_at,
         blocked_by: parse_json_array_uuids(&get_text(row, 8))?,
+        position: row.get::<i64>(11).unwrap_or_default(),
+        parent_task_id: get_opt_text(row, 12)
+            .map(|raw| parse_uuid(&raw, "matter_tasks.parent_task_id"))
+            .transpose()?,
+        checklist: parse_task_checklist(&get_text(row, 13))?,
         created_at: parse_timestamp(&get_text(row, 9))
             .map_err(|e| DatabaseError::Serialization(e.to_string()))?,
         updated_at: parse_timestamp(&get_text(row, 10))
@@ -245,6 +321,9 @@ fn row_to_matter_task_record(row: &libsql::Row) -> Result<MatterTaskRecord, Data
     })
 }
 
+const MATTER_NOTE_COLUMNS: &str =
+    "id, user_id, matter_id, author, body, pinned, created_at, updated_at, parent_id";
+
 fn row_to_matter_note_record(row: &libsql::Row) -> Result<MatterNoteRecord, DatabaseError> {
     Ok(MatterNoteRecord {
         id: parse_uuid(&get_text(row, 0), "matter_notes.id")?,
@@ -253,6 +332,9 @@ fn row_to_matter_note_record(row: &libsql::Row) -> Result<MatterNoteRecord, Data
         author: get_text(row, 3),
         body: get_text(row, 4),
         pinned: get_i64(row, 5) != 0,
+        parent_id: get_opt_text(row, 8)
+            .map(|raw| parse_uuid(&raw, "matter_notes.parent_id"))
+            .transpose()?,
         created_at: parse_timestamp(&get_text(row, 6))
             .map_err(|e| DatabaseError::Serialization(e.to_string()))?,
         updated_at: parse_timestamp(&get_text(row, 7))
@@ -788,11 +870,37 @@ impl RbacStore for LibSqlBackend {
             .await?
             .next()
             .await?;
-        let Some(row) = row else {
-            return Ok(None);
+        let member_role = match row {
+            Some(row) => Some(parse_matter_member_role(&get_text(&row, 0))?),
+            None => None,
         };
-        let role_raw = get_text(&row, 0);
-        Ok(Some(parse_matter_member_role(&role_raw)?))
+        let org_row = conn
+            .query(
+                "SELECT om.role FROM matters m \
+                 JOIN organization_members om ON om.org_id = m.org_id \
+                 WHERE m.user_id = ?1 AND m.matter_id = ?2 AND om.user_id = ?3 \
+                 LIMIT 1",
+                params![matter_owner_user_id, matter_id, requesting_user_id],
+            )
+            .await?
+            .next()
+            .await?;
+        let org_role = match org_row {
+            Some(org_row) => {
+                let org_role_raw = get_text(&org_row, 0);
+                let org_role = OrgRole::from_db_value(&org_role_raw).ok_or_else(|| {
+                    DatabaseError::Serialization(format!(
+                        "invalid organization role '{org_role_raw}'"
+                    ))
+                })?;
+                Some(org_role.matter_role())
+            }
+            None => None,
+        };
+        Ok(match (member_role, org_role) {
+            (Some(member), Some(org)) => Some(member.stronger(org)),
+            (member, org) => member.or(org),
+        })
     }
 
     async fn remove_matter_membership(
@@ -1215,8 +1323,10 @@ impl MatterTaskStore for LibSqlBackend {
         let conn = self.connect().await?;
         let mut rows = conn
             .query(
-                "SELECT id, user_id, matter_id, title, description, status, assignee, due_at, blocked_by, created_at, updated_at \
-                 FROM matter_tasks WHERE user_id = ?1 AND matter_id = ?2 ORDER BY created_at DESC",
+                &format!(
+                    "SELECT {MATTER_TASK_COLUMNS} \
+                 FROM matter_tasks WHERE user_id = ?1 AND matter_id = ?2 ORDER BY created_at DESC"
+                ),
                 params![user_id, matter_id],
             )
             .await?;
@@ -1242,13 +1352,18 @@ impl MatterTaskStore for LibSqlBackend {
                 .collect::<Vec<_>>(),
         )
         .map_err(|e| DatabaseError::Serialization(e.to_string()))?;
+        let checklist = task_checklist_json(&input.checklist)?;
 
         let conn = self.connect().await?;
         let task_id = Uuid::new_v4();
         conn.execute(
             "INSERT INTO matter_tasks \
-             (id, user_id, matter_id, title, description, status, assignee, due_at, blocked_by, created_at, updated_at) \
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, datetime('now'), datetime('now'))",
+             (id, user_id, matter_id, title, description, status, assignee, due_at, blocked_by, position, \
+              parent_task_id, checklist, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, \
+                     (SELECT COALESCE(MAX(position), -1) + 1 FROM matter_tasks \
+                      WHERE user_id = ?2 AND matter_id = ?3 AND status = ?6), \
+                     ?10, ?11, datetime('now'), datetime('now'))",
             params![
                 task_id.to_string(),
                 user_id,
@@ -1259,20 +1374,26 @@ impl MatterTaskStore for LibSqlBackend {
                 opt_text(input.assignee.as_deref()),
                 opt_text_owned(input.due_at.as_ref().map(fmt_ts)),
                 blocked_by,
+                opt_text_owned(input.parent_task_id.map(|id| id.to_string())),
+                checklist,
             ],
         )
         .await?;
 
         let row = conn
             .query(
-                "SELECT id, user_id, matter_id, title, description, status, assignee, due_at, blocked_by, created_at, updated_at \
-                 FROM matter_tasks WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 LIMIT 1",
+                &format!(
+                    "SELECT {MATTER_TASK_COLUMNS} \
+                 FROM matter_tasks WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 LIMIT 1"
+                ),
                 params![user_id, matter_id, task_id.to_string()],
             )
             .await?
             .next()
             .await?
-            .ok_or_else(|| DatabaseError::Query("failed to load created matter task".to_string()))?;
+            .ok_or_else(|| {
+                DatabaseError::Query("failed to load created matter task".to_string())
+            })?;
 
         row_to_matter_task_record(&row)
     }
@@ -1287,8 +1408,10 @@ impl MatterTaskStore for LibSqlBackend {
         let conn = self.connect().await?;
         let existing_row = conn
             .query(
-                "SELECT id, user_id, matter_id, title, description, status, assignee, due_at, blocked_by, created_at, updated_at \
-                 FROM matter_tasks WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 LIMIT 1",
+                &format!(
+                    "SELECT {MATTER_TASK_COLUMNS} \
+                 FROM matter_tasks WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 LIMIT 1"
+                ),
                 params![user_id, matter_id, task_id.to_string()],
             )
             .await?
@@ -1312,6 +1435,13 @@ impl MatterTaskStore for LibSqlBackend {
                 .collect::<Vec<_>>(),
         )
         .map_err(|e| DatabaseError::Serialization(e.to_string()))?;
+        let merged_parent = input.parent_task_id.unwrap_or(existing.parent_task_id);
+        let checklist = task_checklist_json(
+            input
+                .checklist
+                .as_deref()
+                .unwrap_or(existing.checklist.as_slice()),
+        )?;
 
         conn.execute(
             "UPDATE matter_tasks SET \
@@ -1321,6 +1451,11 @@ impl MatterTaskStore for LibSqlBackend {
                 assignee = ?7, \
                 due_at = ?8, \
                 blocked_by = ?9, \
+                position = CASE WHEN status = ?6 THEN position ELSE \
+                    (SELECT COALESCE(MAX(position), -1) + 1 FROM matter_tasks \
+                     WHERE user_id = ?1 AND matter_id = ?2 AND status = ?6) END, \
+                parent_task_id = ?10, \
+                checklist = ?11, \
                 updated_at = datetime('now') \
              WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3",
             params![
@@ -1333,14 +1468,22 @@ impl MatterTaskStore for LibSqlBackend {
                 opt_text(merged_assignee.as_deref()),
                 opt_text_owned(merged_due_at.as_ref().map(fmt_ts)),
                 blocked_by,
+                opt_text_owned(merged_parent.map(|id| id.to_string())),
+                checklist,
             ],
         )
         .await?;
+        roll_up_matter_task_parents(&conn, user_id, matter_id, merged_parent).await?;
+        if existing.parent_task_id != merged_parent {
+            roll_up_matter_task_parents(&conn, user_id, matter_id, existing.parent_task_id).await?;
+        }
 
         let updated = conn
             .query(
-                "SELECT id, user_id, matter_id, title, description, status, assignee, due_at, blocked_by, created_at, updated_at \
-                 FROM matter_tasks WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 LIMIT 1",
+                &format!(
+                    "SELECT {MATTER_TASK_COLUMNS} \
+                 FROM matter_tasks WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 LIMIT 1"
+                ),
                 params![user_id, matter_id, task_id.to_string()],
             )
             .await?
@@ -1358,14 +1501,87 @@ impl MatterTaskStore for LibSqlBackend {
         task_id: Uuid,
     ) -> Result<bool, DatabaseError> {
         let conn = self.connect().await?;
+        let parent_id = matter_task_parent_id(&conn, user_id, matter_id, task_id).await?;
+        conn.execute(
+            "UPDATE matter_tasks SET parent_task_id = NULL, updated_at = datetime('now') \
+             WHERE user_id = ?1 AND matter_id = ?2 AND parent_task_id = ?3",
+            params![user_id, matter_id, task_id.to_string()],
+        )
+        .await?;
         let deleted = conn
             .execute(
                 "DELETE FROM matter_tasks WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3",
                 params![user_id, matter_id, task_id.to_string()],
             )
             .await?;
+        if deleted > 0 {
+            roll_up_matter_task_parents(&conn, user_id, matter_id, parent_id).await?;
+        }
         Ok(deleted > 0)
     }
+
+    async fn move_matter_tasks(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        moves: &[MatterTaskMove],
+    ) -> Result<bool, DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute("BEGIN", ()).await?;
+
+        let op_result: Result<bool, DatabaseError> = async {
+            for step in moves {
+                let (set_assignee, assignee) = match &step.assignee {
+                    Some(assignee) => (1, assignee.as_deref()),
+                    None => (0, None),
+                };
+                let updated = conn
+                    .execute(
+                        "UPDATE matter_tasks SET \
+                            status = COALESCE(?4, status), \
+                            assignee = CASE WHEN ?5 = 1 THEN ?6 ELSE assignee END, \
+                            position = ?7, \
+                            updated_at = datetime('now') \
+                         WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3",
+                        params![
+                            user_id,
+                            matter_id,
+                            step.task_id.to_string(),
+                            opt_text(step.status.map(MatterTaskStatus::as_str)),
+                            set_assignee,
+                            opt_text(assignee),
+                            step.position,
+                        ],
+                    )
+                    .await?;
+                if updated == 0 {
+                    return Ok(false);
+                }
+            }
+            for step in moves.iter().filter(|step| step.status.is_some()) {
+                let parent_id =
+                    matter_task_parent_id(&conn, user_id, matter_id, step.task_id).await?;
+                roll_up_matter_task_parents(&conn, user_id, matter_id, parent_id).await?;
+            }
+            Ok(true)
+        }
+        .await;
+
+        match op_result {
+            Ok(true) => {
+                conn.execute("COMMIT", ()).await?;
+                Ok(true)
+            }
+            Ok(false) => {
+                conn.execute("ROLLBACK", ()).await?;
+                Ok(false)
+            }
+            Err(err) => {
+                let _ = conn.execute("ROLLBACK", ()).await;
+                Err(err)
+            }
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -1378,9 +1594,11 @@ impl MatterNoteStore for LibSqlBackend {
         let conn = self.connect().await?;
         let mut rows = conn
             .query(
-                "SELECT id, user_id, matter_id, author, body, pinned, created_at, updated_at \
+                &format!(
+                    "SELECT {MATTER_NOTE_COLUMNS} \
                  FROM matter_notes WHERE user_id = ?1 AND matter_id = ?2 \
-                 ORDER BY pinned DESC, created_at DESC",
+                 ORDER BY pinned DESC, created_at DESC"
+                ),
                 params![user_id, matter_id],
             )
             .await?;
@@ -1401,8 +1619,9 @@ impl MatterNoteStore for LibSqlBackend {
         let conn = self.connect().await?;
         let note_id = Uuid::new_v4();
         conn.execute(
-            "INSERT INTO matter_notes (id, user_id, matter_id, author, body, pinned, created_at, updated_at) \
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, datetime('now'), datetime('now'))",
+            "INSERT INTO matter_notes \
+             (id, user_id, matter_id, author, body, pinned, parent_id, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, datetime('now'), datetime('now'))",
             params![
                 note_id.to_string(),
                 user_id,
@@ -1410,14 +1629,17 @@ impl MatterNoteStore for LibSqlBackend {
                 input.author.as_str(),
                 input.body.as_str(),
                 if input.pinned { 1 } else { 0 },
+                opt_text_owned(input.parent_id.map(|id| id.to_string())),
             ],
         )
         .await?;
 
         let row = conn
             .query(
-                "SELECT id, user_id, matter_id, author, body, pinned, created_at, updated_at \
-                 FROM matter_notes WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 LIMIT 1",
+                &format!(
+                    "SELECT {MATTER_NOTE_COLUMNS} \
+                 FROM matter_notes WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 LIMIT 1"
+                ),
                 params![user_id, matter_id, note_id.to_string()],
             )
             .await?
@@ -1439,8 +1661,10 @@ impl MatterNoteStore for LibSqlBackend {
         let conn = self.connect().await?;
         let existing_row = conn
             .query(
-                "SELECT id, user_id, matter_id, author, body, pinned, created_at, updated_at \
-                 FROM matter_notes WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 LIMIT 1",
+                &format!(
+                    "SELECT {MATTER_NOTE_COLUMNS} \
+                 FROM matter_notes WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 LIMIT 1"
+                ),
                 params![user_id, matter_id, note_id.to_string()],
             )
             .await?
@@ -1475,8 +1699,10 @@ impl MatterNoteStore for LibSqlBackend {
 
         let updated = conn
             .query(
-                "SELECT id, user_id, matter_id, author, body, pinned, created_at, updated_at \
-                 FROM matter_notes WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 LIMIT 1",
+                &format!(
+                    "SELECT {MATTER_NOTE_COLUMNS} \
+                 FROM matter_notes WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 LIMIT 1"
+                ),
                 params![user_id, matter_id, note_id.to_string()],
             )
             .await?
@@ -1500,6 +1726,14 @@ impl MatterNoteStore for LibSqlBackend {
                 params![user_id, matter_id, note_id.to_string()],
             )
             .await?;
+        if deleted > 0 {
+            // Replies have no meaning without the note they answer.
+            conn.execute(
+                "DELETE FROM matter_notes WHERE user_id = ?1 AND matter_id = ?2 AND parent_id = ?3",
+                params![user_id, matter_id, note_id.to_string()],
+            )
+            .await?;
+        }
         Ok(deleted > 0)
     }
 }
@@ -2068,6 +2302,26 @@ impl DocumentVersionStore for LibSqlBackend {
             }
         }
     }
+
+    async fn count_matter_document_versions(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<u64, DatabaseError> {
+        let conn = self.connect().await?;
+        let row = conn
+            .query(
+                "SELECT COUNT(*) \
+                 FROM document_versions v \
+                 JOIN matter_documents md ON md.id = v.matter_document_id \
+                 WHERE v.user_id = ?1 AND md.matter_id = ?2",
+                params![user_id, matter_id],
+            )
+            .await?
+            .next()
+            .await?;
+        Ok(row.map(|row| get_i64(&row, 0).max(0) as u64).unwrap_or(0))
+    }
 }
 
 #[async_trait::async_trait]
@@ -2340,6 +2594,20 @@ impl DocumentTemplateStore for LibSqlBackend {
                 params![user_id, template_id.to_string()],
             )
             .await?;
+        if deleted > 0 {
+            // Foreign keys are not enforced, so clear the library rows by hand.
+            for table in [
+                "document_template_library",
+                "document_template_versions",
+                "document_template_uses",
+            ] {
+                conn.execute(
+                    &format!("DELETE FROM {table} WHERE template_id = ?1"),
+                    params![template_id.to_string()],
+                )
+                .await?;
+            }
+        }
         Ok(deleted > 0)
     }
 }
@@ -3726,13 +3994,16 @@ impl LegalRestoreStore for LibSqlBackend {
         .map_err(|e| DatabaseError::Serialization(e.to_string()))?;
         conn.execute(
             "INSERT INTO matter_tasks \
-             (id, user_id, matter_id, title, description, status, assignee, due_at, blocked_by, created_at, updated_at) \
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11) \
+             (id, user_id, matter_id, title, description, status, assignee, due_at, blocked_by, created_at, updated_at, position, \
+              parent_task_id, checklist) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14) \
              ON CONFLICT(id) DO UPDATE SET \
                user_id=excluded.user_id, matter_id=excluded.matter_id, title=excluded.title, \
                description=excluded.description, status=excluded.status, assignee=excluded.assignee, \
                due_at=excluded.due_at, blocked_by=excluded.blocked_by, \
-               created_at=excluded.created_at, updated_at=excluded.updated_at",
+               created_at=excluded.created_at, updated_at=excluded.updated_at, \
+               position=excluded.position, parent_task_id=excluded.parent_task_id, \
+               checklist=excluded.checklist",
             params![
                 row.id.to_string(),
                 row.user_id.as_str(),
@@ -3745,6 +4016,9 @@ impl LegalRestoreStore for LibSqlBackend {
                 blocked_by.as_str(),
                 fmt_ts(&row.created_at),
                 fmt_ts(&row.updated_at),
+                row.position,
+                opt_text_owned(row.parent_task_id.map(|id| id.to_string())),
+                task_checklist_json(&row.checklist)?,
             ],
         )
         .await?;
@@ -3755,11 +4029,12 @@ impl LegalRestoreStore for LibSqlBackend {
         let conn = self.connect().await?;
         conn.execute(
             "INSERT INTO matter_notes \
-             (id, user_id, matter_id, author, body, pinned, created_at, updated_at) \
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8) \
+             (id, user_id, matter_id, author, body, pinned, created_at, updated_at, parent_id) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9) \
              ON CONFLICT(id) DO UPDATE SET \
                user_id=excluded.user_id, matter_id=excluded.matter_id, author=excluded.author, \
-               body=excluded.body, pinned=excluded.pinned, created_at=excluded.created_at, updated_at=excluded.updated_at",
+               body=excluded.body, pinned=excluded.pinned, created_at=excluded.created_at, updated_at=excluded.updated_at, \
+               parent_id=excluded.parent_id",
             params![
                 row.id.to_string(),
                 row.user_id.as_str(),
@@ -3769,6 +4044,7 @@ impl LegalRestoreStore for LibSqlBackend {
                 row.pinned,
                 fmt_ts(&row.created_at),
                 fmt_ts(&row.updated_at),
+                opt_text_owned(row.parent_id.map(|id| id.to_string())),
             ],
         )
         .await?;
@@ -4109,4 +4385,85 @@ impl LegalRestoreStore for LibSqlBackend {
         .await?;
         Ok(())
     }
+    async fn count_history_rows(&self, user_id: &str) -> Result<HistoryCounts, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                "SELECT \
+                 (SELECT COUNT(*) FROM conversations WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM conversation_messages m \
+                    JOIN conversations c ON c.id = m.conversation_id WHERE c.user_id = ?1), \
+                 (SELECT COUNT(*) FROM agent_jobs WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM routine_runs r \
+                    JOIN routines t ON t.id = r.routine_id WHERE t.user_id = ?1), \
+                 (SELECT COUNT(*) FROM cost_entries WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM memory_document_revisions r \
+                    JOIN memory_documents d ON d.id = r.document_id WHERE d.user_id = ?1), \
+                 (SELECT COUNT(*) FROM memory_trash WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM party_candidates \
+                    WHERE matter_id IN (SELECT matter_id FROM matters WHERE user_id = ?1)), \
+                 (SELECT COUNT(*) FROM document_translations WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM client_status_reports WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM matter_spend_caps WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM sms_consents WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM matter_facts WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM document_entities WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM document_tags WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM document_template_library WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM document_template_versions WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM document_template_uses WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM job_artifacts a \
+                    JOIN agent_jobs j ON j.id = a.job_id WHERE j.user_id = ?1), \
+                 (SELECT COUNT(*) FROM notifications WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM organization_members WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM document_share_links WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM party_watchlist WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM matter_exhibits WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM matter_settlement_proposals WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM matter_settlement_authority WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM client_communication_preferences WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM client_communication_consents WHERE user_id = ?1), \
+                 (SELECT COUNT(*) FROM precedents WHERE user_id = ?1)",
+                params![user_id],
+            )
+            .await?;
+        let row = rows
+            .next()
+            .await?
+            .ok_or_else(|| DatabaseError::Query("history count returned no row".to_string()))?;
+        let count = |index: i32| -> Result<usize, DatabaseError> {
+            Ok(row.get::<i64>(index)?.max(0) as usize)
+        };
+        Ok(HistoryCounts {
+            conversations: count(0)?,
+            conversation_messages: count(1)?,
+            jobs: count(2)?,
+            routine_runs: count(3)?,
+            cost_entries: count(4)?,
+            document_revisions: count(5)?,
+            trashed_documents: count(6)?,
+            party_candidates: count(7)?,
+            translations: count(8)?,
+            status_reports: count(9)?,
+            spend_caps: count(10)?,
+            sms_consents: count(11)?,
+            matter_facts: count(12)?,
+            document_entities: count(13)?,
+            document_tags: count(14)?,
+            library_templates: count(15)?,
+            template_versions: count(16)?,
+            template_uses: count(17)?,
+            job_artifacts: count(18)?,
+            notifications: count(19)?,
+            org_memberships: count(20)?,
+            share_links: count(21)?,
+            watchlist_parties: count(22)?,
+            exhibits: count(23)?,
+            settlement_proposals: count(24)?,
+            settlement_authority: count(25)?,
+            comm_preferences: count(26)?,
+            comm_consents: count(27)?,
+            precedents: count(28)?,
+        })
+    }
 }
@@ -0,0 +1,149 @@
+//! LlmResponseCacheStore implementation for LibSqlBackend.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use libsql::params;
+
+use super::{LibSqlBackend, fmt_ts, get_i64, get_text, get_ts};
+use crate::db::{LlmCacheEntry, LlmResponseCacheStore};
+use crate::error::DatabaseError;
+
+#[async_trait]
+impl LlmResponseCacheStore for LibSqlBackend {
+    async fn get_llm_cache_entry(
+        &self,
+        cache_key: &str,
+    ) -> Result<Option<LlmCacheEntry>, DatabaseError> {
+        let conn = self.connect().await?;
+        let now = fmt_ts(&Utc::now());
+        let updated = conn
+            .execute(
+                "UPDATE llm_response_cache SET hit_count = hit_count + 1 \
+                 WHERE cache_key = ?1 AND expires_at > ?2",
+                params![cache_key, now],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        if updated == 0 {
+            return Ok(None);
+        }
+        let mut rows = conn
+            .query(
+                "SELECT cache_key, model, response, created_at, expires_at, hit_count \
+                 FROM llm_response_cache WHERE cache_key = ?1",
+                params![cache_key],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        let response = serde_json::from_str(&self.open_db(get_text(&row, 2))?)
+            .map_err(|e| DatabaseError::Serialization(e.to_string()))?;
+        Ok(Some(LlmCacheEntry {
+            cache_key: get_text(&row, 0),
+            model: get_text(&row, 1),
+            response,
+            created_at: get_ts(&row, 3),
+            expires_at: get_ts(&row, 4),
+            hit_count: get_i64(&row, 5),
+        }))
+    }
+
+    async fn put_llm_cache_entry(
+        &self,
+        cache_key: &str,
+        model: &str,
+        response: &serde_json::Value,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), DatabaseError> {
+        let response = response.to_string();
+        let stored = self.seal_db(&response)?;
+        let conn = self.connect().await?;
+        conn.execute(
+            "INSERT INTO llm_response_cache \
+             (cache_key, model, response, created_at, expires_at, hit_count) \
+             VALUES (?1, ?2, ?3, ?4, ?5, 0) \
+             ON CONFLICT (cache_key) DO UPDATE SET \
+               model = excluded.model, \
+               response = excluded.response, \
+               created_at = excluded.created_at, \
+               expires_at = excluded.expires_at, \
+               hit_count = 0",
+            params![
+                cache_key,
+                model,
+                stored.as_ref(),
+                fmt_ts(&Utc::now()),
+                fmt_ts(&expires_at)
+            ],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn purge_expired_llm_cache_entries(&self) -> Result<u64, DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute(
+            "DELETE FROM llm_response_cache WHERE expires_at <= ?1",
+            params![fmt_ts(&Utc::now())],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    #[tokio::test]
+    async fn entries_expire_and_count_hits() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let response = serde_json::json!({ "content": "cached" });
+
+        assert!(db.get_llm_cache_entry("k1").await.unwrap().is_none());
+        db.put_llm_cache_entry(
+            "k1",
+            "model-a",
+            &response,
+            chrono::Utc::now() + Duration::hours(1),
+        )
+        .await
+        .unwrap();
+        db.put_llm_cache_entry(
+            "k2",
+            "model-a",
+            &response,
+            chrono::Utc::now() - Duration::seconds(1),
+        )
+        .await
+        .unwrap();
+
+        let hit = db
+            .get_llm_cache_entry("k1")
+            .await
+            .unwrap()
+            .expect("live entry");
+        assert_eq!(hit.model, "model-a");
+        assert_eq!(hit.response, response);
+        assert_eq!(hit.hit_count, 1);
+        assert_eq!(
+            db.get_llm_cache_entry("k1")
+                .await
+                .unwrap()
+                .unwrap()
+                .hit_count,
+            2
+        );
+        assert!(db.get_llm_cache_entry("k2").await.unwrap().is_none());
+
+        assert_eq!(db.purge_expired_llm_cache_entries().await.unwrap(), 1);
+        assert!(db.get_llm_cache_entry("k1").await.unwrap().is_some());
+    }
+}
@@ -0,0 +1,245 @@
+//! MatterExhibitStore implementation for LibSqlBackend.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use libsql::params;
+use uuid::Uuid;
+
+use super::{LibSqlBackend, fmt_ts, get_i64, get_opt_text, get_opt_ts, get_text, get_ts, opt_text};
+use crate::db::{
+    CreateMatterExhibitParams, ExhibitStatus, MatterExhibitRecord, MatterExhibitStore,
+};
+use crate::error::DatabaseError;
+
+const EXHIBIT_COLUMNS: &str = "id, user_id, matter_id, party_prefix, sequence, label, path, \
+     matter_document_id, description, status, created_by, created_at, withdrawn_at";
+
+fn row_to_exhibit(row: &libsql::Row) -> Result<MatterExhibitRecord, DatabaseError> {
+    let status_raw = get_text(row, 9);
+    Ok(MatterExhibitRecord {
+        id: get_text(row, 0)
+            .parse()
+            .map_err(|e: uuid::Error| DatabaseError::Serialization(e.to_string()))?,
+        user_id: get_text(row, 1),
+        matter_id: get_text(row, 2),
+        party_prefix: get_text(row, 3),
+        sequence: get_i64(row, 4) as i32,
+        label: get_text(row, 5),
+        path: get_text(row, 6),
+        matter_document_id: get_opt_text(row, 7)
+            .map(|raw| raw.parse())
+            .transpose()
+            .map_err(|e: uuid::Error| DatabaseError::Serialization(e.to_string()))?,
+        description: get_opt_text(row, 8),
+        status: ExhibitStatus::from_db_value(&status_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("unknown exhibit status '{status_raw}'"))
+        })?,
+        created_by: get_text(row, 10),
+        created_at: get_ts(row, 11),
+        withdrawn_at: get_opt_ts(row, 12),
+    })
+}
+
+#[async_trait]
+impl MatterExhibitStore for LibSqlBackend {
+    async fn create_matter_exhibit(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        input: &CreateMatterExhibitParams,
+    ) -> Result<MatterExhibitRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "INSERT INTO matter_exhibits \
+                     (id, user_id, matter_id, party_prefix, sequence, label, path, \
+                      matter_document_id, description, status, created_by, created_at) \
+                     SELECT ?1, ?2, ?3, ?4, next.n, ?4 || '-' || next.n, ?5, ?6, ?7, ?8, ?9, ?10 \
+                     FROM (SELECT COALESCE(MAX(sequence), 0) + 1 AS n FROM matter_exhibits \
+                           WHERE user_id = ?2 AND matter_id = ?3 AND party_prefix = ?4) AS next \
+                     RETURNING {EXHIBIT_COLUMNS}"
+                ),
+                params![
+                    Uuid::new_v4().to_string(),
+                    user_id,
+                    matter_id,
+                    input.party_prefix.as_str(),
+                    input.path.as_str(),
+                    opt_text(input.matter_document_id.map(|id| id.to_string()).as_deref()),
+                    opt_text(input.description.as_deref()),
+                    ExhibitStatus::Marked.as_str(),
+                    input.created_by.as_str(),
+                    fmt_ts(&Utc::now()),
+                ],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let row = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+            .ok_or_else(|| DatabaseError::Query("exhibit insert returned no row".to_string()))?;
+        row_to_exhibit(&row)
+    }
+
+    async fn list_matter_exhibits(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<Vec<MatterExhibitRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {EXHIBIT_COLUMNS} FROM matter_exhibits \
+                     WHERE user_id = ?1 AND matter_id = ?2 ORDER BY party_prefix, sequence"
+                ),
+                params![user_id, matter_id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let mut exhibits = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            exhibits.push(row_to_exhibit(&row)?);
+        }
+        Ok(exhibits)
+    }
+
+    async fn withdraw_matter_exhibit(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        exhibit_id: Uuid,
+    ) -> Result<Option<MatterExhibitRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "UPDATE matter_exhibits SET status = ?4, \
+                       withdrawn_at = COALESCE(withdrawn_at, ?5) \
+                     WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 \
+                     RETURNING {EXHIBIT_COLUMNS}"
+                ),
+                params![
+                    user_id,
+                    matter_id,
+                    exhibit_id.to_string(),
+                    ExhibitStatus::Withdrawn.as_str(),
+                    fmt_ts(&Utc::now()),
+                ],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        match rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            Some(row) => Ok(Some(row_to_exhibit(&row)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::{
+        ClientType, CreateClientParams, CreateMatterExhibitParams, ExhibitStatus, MatterStatus,
+        UpsertMatterParams,
+    };
+
+    fn exhibit(prefix: &str, path: &str) -> CreateMatterExhibitParams {
+        CreateMatterExhibitParams {
+            party_prefix: prefix.to_string(),
+            path: path.to_string(),
+            matter_document_id: None,
+            description: None,
+            created_by: "default".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn exhibits_number_per_prefix_and_keep_labels_when_withdrawn() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let client = db
+            .create_client(
+                "default",
+                &CreateClientParams {
+                    name: "Acme".to_string(),
+                    client_type: ClientType::Entity,
+                    email: None,
+                    phone: None,
+                    address: None,
+                    notes: None,
+                },
+            )
+            .await
+            .unwrap();
+        db.upsert_matter(
+            "default",
+            &UpsertMatterParams {
+                matter_id: "demo".to_string(),
+                client_id: client.id,
+                status: MatterStatus::Active,
+                stage: None,
+                practice_area: None,
+                jurisdiction: None,
+                opened_at: None,
+                closed_at: None,
+                assigned_to: Vec::new(),
+                custom_fields: serde_json::json!({}),
+            },
+        )
+        .await
+        .unwrap();
+
+        let p1 = db
+            .create_matter_exhibit("default", "demo", &exhibit("P", "matters/demo/a.md"))
+            .await
+            .unwrap();
+        let p2 = db
+            .create_matter_exhibit("default", "demo", &exhibit("P", "matters/demo/b.md"))
+            .await
+            .unwrap();
+        let d1 = db
+            .create_matter_exhibit("default", "demo", &exhibit("D", "matters/demo/c.md"))
+            .await
+            .unwrap();
+        assert_eq!((p1.label.as_str(), p1.sequence), ("P-1", 1));
+        assert_eq!(p2.label, "P-2");
+        assert_eq!(d1.label, "D-1");
+
+        let withdrawn = db
+            .withdraw_matter_exhibit("default", "demo", p2.id)
+            .await
+            .unwrap()
+            .expect("exhibit exists");
+        assert_eq!(withdrawn.status, ExhibitStatus::Withdrawn);
+        assert!(withdrawn.withdrawn_at.is_some());
+        let p3 = db
+            .create_matter_exhibit("default", "demo", &exhibit("P", "matters/demo/d.md"))
+            .await
+            .unwrap();
+        assert_eq!(p3.label, "P-3");
+
+        let labels: Vec<String> = db
+            .list_matter_exhibits("default", "demo")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|exhibit| exhibit.label)
+            .collect();
+        assert_eq!(labels, vec!["D-1", "P-1", "P-2", "P-3"]);
+        assert!(
+            db.withdraw_matter_exhibit("default", "other", p1.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,423 @@
+//! MatterSettlementStore implementation for LibSqlBackend.
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use libsql::params;
+use uuid::Uuid;
+
+use super::{
+    LibSqlBackend, fmt_ts, get_decimal, get_opt_decimal, get_opt_text, get_text, get_ts, opt_text,
+};
+use crate::db::{
+    CreateSettlementAuthorityParams, CreateSettlementProposalParams, MatterSettlementStore,
+    SettlementAuthorityKind, SettlementAuthorityRecord, SettlementProposalKind,
+    SettlementProposalRecord, SettlementProposalStatus, SettlementSide,
+};
+use crate::error::DatabaseError;
+
+const PROPOSAL_COLUMNS: &str = "id, user_id, matter_id, kind, side, from_party, to_party, amount, \
+     terms, proposed_on, expires_on, status, response_notes, created_by, created_at, updated_at";
+
+const AUTHORITY_COLUMNS: &str =
+    "id, user_id, matter_id, kind, amount, granted_on, granted_by, notes, created_by, created_at";
+
+fn parse_naive_date(raw: &str, field: &str) -> Result<NaiveDate, DatabaseError> {
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d").map_err(|_| {
+        DatabaseError::Serialization(format!("invalid {field} '{raw}' (expected YYYY-MM-DD)"))
+    })
+}
+
+fn parse_id(raw: &str) -> Result<Uuid, DatabaseError> {
+    raw.parse()
+        .map_err(|e: uuid::Error| DatabaseError::Serialization(e.to_string()))
+}
+
+fn row_to_proposal(row: &libsql::Row) -> Result<SettlementProposalRecord, DatabaseError> {
+    let kind_raw = get_text(row, 3);
+    let side_raw = get_text(row, 4);
+    let status_raw = get_text(row, 11);
+    Ok(SettlementProposalRecord {
+        id: parse_id(&get_text(row, 0))?,
+        user_id: get_text(row, 1),
+        matter_id: get_text(row, 2),
+        kind: SettlementProposalKind::from_db_value(&kind_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("unknown settlement proposal kind '{kind_raw}'"))
+        })?,
+        side: SettlementSide::from_db_value(&side_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("unknown settlement side '{side_raw}'"))
+        })?,
+        from_party: get_text(row, 5),
+        to_party: get_opt_text(row, 6),
+        amount: get_opt_decimal(row, 7),
+        terms: get_opt_text(row, 8),
+        proposed_on: parse_naive_date(&get_text(row, 9), "proposed_on")?,
+        expires_on: get_opt_text(row, 10)
+            .map(|raw| parse_naive_date(&raw, "expires_on"))
+            .transpose()?,
+        status: SettlementProposalStatus::from_db_value(&status_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("unknown settlement status '{status_raw}'"))
+        })?,
+        response_notes: get_opt_text(row, 12),
+        created_by: get_text(row, 13),
+        created_at: get_ts(row, 14),
+        updated_at: get_ts(row, 15),
+    })
+}
+
+fn row_to_authority(row: &libsql::Row) -> Result<SettlementAuthorityRecord, DatabaseError> {
+    let kind_raw = get_text(row, 3);
+    Ok(SettlementAuthorityRecord {
+        id: parse_id(&get_text(row, 0))?,
+        user_id: get_text(row, 1),
+        matter_id: get_text(row, 2),
+        kind: SettlementAuthorityKind::from_db_value(&kind_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("unknown settlement authority kind '{kind_raw}'"))
+        })?,
+        amount: get_decimal(row, 4),
+        granted_on: parse_naive_date(&get_text(row, 5), "granted_on")?,
+        granted_by: get_opt_text(row, 6),
+        notes: get_opt_text(row, 7),
+        created_by: get_text(row, 8),
+        created_at: get_ts(row, 9),
+    })
+}
+
+#[async_trait]
+impl MatterSettlementStore for LibSqlBackend {
+    async fn create_settlement_proposal(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        input: &CreateSettlementProposalParams,
+    ) -> Result<SettlementProposalRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        let now = fmt_ts(&Utc::now());
+        let mut rows = conn
+            .query(
+                &format!(
+                    "INSERT INTO matter_settlement_proposals \
+                     (id, user_id, matter_id, kind, side, from_party, to_party, amount, terms, \
+                      proposed_on, expires_on, status, created_by, created_at, updated_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?14) \
+                     RETURNING {PROPOSAL_COLUMNS}"
+                ),
+                params![
+                    Uuid::new_v4().to_string(),
+                    user_id,
+                    matter_id,
+                    input.kind.as_str(),
+                    input.side.as_str(),
+                    input.from_party.as_str(),
+                    opt_text(input.to_party.as_deref()),
+                    opt_text(input.amount.map(|amount| amount.to_string()).as_deref()),
+                    opt_text(input.terms.as_deref()),
+                    input.proposed_on.format("%Y-%m-%d").to_string(),
+                    opt_text(
+                        input
+                            .expires_on
+                            .map(|date| date.format("%Y-%m-%d").to_string())
+                            .as_deref()
+                    ),
+                    SettlementProposalStatus::Open.as_str(),
+                    input.created_by.as_str(),
+                    now,
+                ],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let row = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+            .ok_or_else(|| {
+                DatabaseError::Query("settlement proposal insert returned no row".to_string())
+            })?;
+        row_to_proposal(&row)
+    }
+
+    async fn list_settlement_proposals(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<Vec<SettlementProposalRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {PROPOSAL_COLUMNS} FROM matter_settlement_proposals \
+                     WHERE user_id = ?1 AND matter_id = ?2 ORDER BY proposed_on, created_at"
+                ),
+                params![user_id, matter_id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let mut proposals = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            proposals.push(row_to_proposal(&row)?);
+        }
+        Ok(proposals)
+    }
+
+    async fn update_settlement_proposal_status(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        proposal_id: Uuid,
+        status: SettlementProposalStatus,
+        response_notes: Option<&str>,
+    ) -> Result<Option<SettlementProposalRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "UPDATE matter_settlement_proposals SET status = ?4, \
+                       response_notes = COALESCE(?5, response_notes), updated_at = ?6 \
+                     WHERE user_id = ?1 AND matter_id = ?2 AND id = ?3 \
+                     RETURNING {PROPOSAL_COLUMNS}"
+                ),
+                params![
+                    user_id,
+                    matter_id,
+                    proposal_id.to_string(),
+                    status.as_str(),
+                    opt_text(response_notes),
+                    fmt_ts(&Utc::now()),
+                ],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        match rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            Some(row) => Ok(Some(row_to_proposal(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn create_settlement_authority(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        input: &CreateSettlementAuthorityParams,
+    ) -> Result<SettlementAuthorityRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "INSERT INTO matter_settlement_authority \
+                     (id, user_id, matter_id, kind, amount, granted_on, granted_by, notes, \
+                      created_by, created_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10) \
+                     RETURNING {AUTHORITY_COLUMNS}"
+                ),
+                params![
+                    Uuid::new_v4().to_string(),
+                    user_id,
+                    matter_id,
+                    input.kind.as_str(),
+                    input.amount.to_string(),
+                    input.granted_on.format("%Y-%m-%d").to_string(),
+                    opt_text(input.granted_by.as_deref()),
+                    opt_text(input.notes.as_deref()),
+                    input.created_by.as_str(),
+                    fmt_ts(&Utc::now()),
+                ],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let row = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+            .ok_or_else(|| {
+                DatabaseError::Query("settlement authority insert returned no row".to_string())
+            })?;
+        row_to_authority(&row)
+    }
+
+    async fn list_settlement_authority(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<Vec<SettlementAuthorityRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {AUTHORITY_COLUMNS} FROM matter_settlement_authority \
+                     WHERE user_id = ?1 AND matter_id = ?2 \
+                     ORDER BY granted_on DESC, created_at DESC"
+                ),
+                params![user_id, matter_id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let mut grants = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            grants.push(row_to_authority(&row)?);
+        }
+        Ok(grants)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use crate::db::{
+        ClientType, CreateClientParams, CreateSettlementAuthorityParams,
+        CreateSettlementProposalParams, MatterStatus, SettlementAuthorityKind,
+        SettlementProposalKind, SettlementProposalStatus, SettlementSide, UpsertMatterParams,
+    };
+
+    fn date(raw: &str) -> chrono::NaiveDate {
+        chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d").unwrap()
+    }
+
+    #[tokio::test]
+    async fn proposals_list_in_order_and_latest_authority_comes_first() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let client = db
+            .create_client(
+                "default",
+                &CreateClientParams {
+                    name: "Acme".to_string(),
+                    client_type: ClientType::Entity,
+                    email: None,
+                    phone: None,
+                    address: None,
+                    notes: None,
+                },
+            )
+            .await
+            .unwrap();
+        db.upsert_matter(
+            "default",
+            &UpsertMatterParams {
+                matter_id: "demo".to_string(),
+                client_id: client.id,
+                status: MatterStatus::Active,
+                stage: None,
+                practice_area: None,
+                jurisdiction: None,
+                opened_at: None,
+                closed_at: None,
+                assigned_to: Vec::new(),
+                custom_fields: serde_json::json!({}),
+            },
+        )
+        .await
+        .unwrap();
+
+        let proposal = |kind, side, amount: i64, on: &str| CreateSettlementProposalParams {
+            kind,
+            side,
+            from_party: "Party".to_string(),
+            to_party: None,
+            amount: Some(Decimal::from(amount)),
+            terms: Some("Mutual release".to_string()),
+            proposed_on: date(on),
+            expires_on: None,
+            created_by: "default".to_string(),
+        };
+        let offer = db
+            .create_settlement_proposal(
+                "default",
+                "demo",
+                &proposal(
+                    SettlementProposalKind::Offer,
+                    SettlementSide::Opposing,
+                    50_000,
+                    "2025-03-10",
+                ),
+            )
+            .await
+            .unwrap();
+        db.create_settlement_proposal(
+            "default",
+            "demo",
+            &proposal(
+                SettlementProposalKind::Demand,
+                SettlementSide::Client,
+                250_000,
+                "2025-02-01",
+            ),
+        )
+        .await
+        .unwrap();
+
+        let proposals = db
+            .list_settlement_proposals("default", "demo")
+            .await
+            .unwrap();
+        let amounts: Vec<Option<Decimal>> = proposals.iter().map(|p| p.amount).collect();
+        assert_eq!(
+            amounts,
+            vec![Some(Decimal::from(250_000)), Some(Decimal::from(50_000))]
+        );
+        assert_eq!(proposals[0].status, SettlementProposalStatus::Open);
+
+        let countered = db
+            .update_settlement_proposal_status(
+                "default",
+                "demo",
+                offer.id,
+                SettlementProposalStatus::Countered,
+                Some("Countered at mediation"),
+            )
+            .await
+            .unwrap()
+            .expect("proposal exists");
+        assert_eq!(countered.status, SettlementProposalStatus::Countered);
+        assert_eq!(
+            countered.response_notes.as_deref(),
+            Some("Countered at mediation")
+        );
+        assert!(
+            db.update_settlement_proposal_status(
+                "default",
+                "other",
+                offer.id,
+                SettlementProposalStatus::Rejected,
+                None,
+            )
+            .await
+            .unwrap()
+            .is_none()
+        );
+
+        for (amount, on) in [(150_000, "2025-01-15"), (120_000, "2025-03-01")] {
+            db.create_settlement_authority(
+                "default",
+                "demo",
+                &CreateSettlementAuthorityParams {
+                    kind: SettlementAuthorityKind::Minimum,
+                    amount: Decimal::from(amount),
+                    granted_on: date(on),
+                    granted_by: Some("J. Client".to_string()),
+                    notes: None,
+                    created_by: "default".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        }
+        let grants = db
+            .list_settlement_authority("default", "demo")
+            .await
+            .unwrap();
+        assert_eq!(grants.len(), 2);
+        assert_eq!(grants[0].amount, Decimal::from(120_000));
+        assert_eq!(grants[0].kind, SettlementAuthorityKind::Minimum);
+    }
+}
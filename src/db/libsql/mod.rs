@@ -6,14 +6,30 @@
 //! - Turso cloud with embedded replica (sync to cloud)
 //! - In-memory (for testing)
 
+mod client_communications;
 mod conversations;
+mod cost_ledger;
+mod document_entities;
+mod document_shares;
+mod document_tags;
+mod encryption;
+mod facts;
 mod jobs;
 mod legal_conflicts;
 mod legal_hardening;
 mod legal_practice;
+mod llm_cache;
+mod matter_exhibits;
+mod matter_settlement;
+mod notifications;
+mod organizations;
+mod party_watchlist;
+mod precedents;
 mod routines;
 mod sandbox;
 mod settings;
+mod sms_consent;
+mod template_library;
 mod tool_failures;
 mod workspace;
 
@@ -42,7 +58,7 @@ pub(crate) const ROUTINE_COLUMNS: &str = "\
     cooldown_secs, max_concurrent, dedup_window_secs, \
     notify_channel, notify_user, notify_on_success, notify_on_failure, notify_on_attention, \
     state, last_run_at, next_fire_at, run_count, consecutive_failures, \
-    created_at, updated_at";
+    created_at, updated_at, urgent, execution_override, chain";
 
 /// Explicit column list for routine_runs table (matches positional access in `row_to_routine_run_libsql`).
 pub(crate) const ROUTINE_RUN_COLUMNS: &str = "\
@@ -56,6 +72,8 @@ pub(crate) const ROUTINE_RUN_COLUMNS: &str = "\
 /// create their own connections per-operation.
 pub struct LibSqlBackend {
     db: Arc<LibSqlDatabase>,
+    /// Seals content columns at rest when `LIBSQL_ENCRYPT_CONTENT` is on.
+    cipher: Option<Arc<encryption::ContentCipher>>,
 }
 
 impl LibSqlBackend {
@@ -73,7 +91,10 @@ impl LibSqlBackend {
             .await
             .map_err(|e| DatabaseError::Pool(format!("Failed to open libSQL database: {}", e)))?;
 
-        Ok(Self { db: Arc::new(db) })
+        Ok(Self {
+            db: Arc::new(db),
+            cipher: None,
+        })
     }
 
     /// Create a new in-memory database (for testing).
@@ -85,7 +106,10 @@ impl LibSqlBackend {
                 DatabaseError::Pool(format!("Failed to create in-memory database: {}", e))
             })?;
 
-        Ok(Self { db: Arc::new(db) })
+        Ok(Self {
+            db: Arc::new(db),
+            cipher: None,
+        })
     }
 
     /// Create with Turso cloud sync (embedded replica).
@@ -105,7 +129,10 @@ impl LibSqlBackend {
             .await
             .map_err(|e| DatabaseError::Pool(format!("Failed to open remote replica: {}", e)))?;
 
-        Ok(Self { db: Arc::new(db) })
+        Ok(Self {
+            db: Arc::new(db),
+            cipher: None,
+        })
     }
 
     /// Get a shared reference to the underlying database handle.
@@ -473,6 +500,39 @@ CREATE INDEX IF NOT EXISTS idx_trust_reconciliations_account
         )
         .await?;
         ensure_libsql_column(&conn, "ALTER TABLE time_entries ADD COLUMN task_code TEXT").await?;
+        ensure_libsql_column(
+            &conn,
+            "ALTER TABLE routines ADD COLUMN urgent INTEGER NOT NULL DEFAULT 0",
+        )
+        .await?;
+        ensure_libsql_column(
+            &conn,
+            "ALTER TABLE routines ADD COLUMN execution_override TEXT NOT NULL DEFAULT '{}'",
+        )
+        .await?;
+        ensure_libsql_column(
+            &conn,
+            "ALTER TABLE routines ADD COLUMN chain TEXT NOT NULL DEFAULT '{}'",
+        )
+        .await?;
+        for table in ["matters", "clients", "memory_documents"] {
+            ensure_libsql_column(
+                &conn,
+                &format!(
+                    "ALTER TABLE {table} ADD COLUMN org_id TEXT \
+                     REFERENCES organizations(id) ON DELETE SET NULL"
+                ),
+            )
+            .await?;
+        }
+        conn.execute_batch(
+            "CREATE INDEX IF NOT EXISTS idx_matters_org ON matters(org_id) WHERE org_id IS NOT NULL;
+             CREATE INDEX IF NOT EXISTS idx_clients_org ON clients(org_id) WHERE org_id IS NOT NULL;
+             CREATE INDEX IF NOT EXISTS idx_memory_documents_org
+                 ON memory_documents(org_id) WHERE org_id IS NOT NULL;",
+        )
+        .await
+        .map_err(|e| DatabaseError::Migration(format!("failed to ensure org indexes: {}", e)))?;
         ensure_libsql_column(
             &conn,
             "ALTER TABLE time_entries ADD COLUMN activity_code TEXT",
@@ -613,6 +673,66 @@ CREATE INDEX IF NOT EXISTS idx_trust_reconciliations_account
             "ALTER TABLE matter_deadlines ADD COLUMN is_unsupported INTEGER NOT NULL DEFAULT 0",
         )
         .await?;
+        ensure_libsql_column(
+            &conn,
+            "ALTER TABLE matter_tasks ADD COLUMN position INTEGER NOT NULL DEFAULT 0",
+        )
+        .await?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_matter_tasks_board \
+             ON matter_tasks(user_id, matter_id, status, position)",
+            (),
+        )
+        .await
+        .map_err(|e| {
+            DatabaseError::Migration(format!("failed to ensure matter task board index: {}", e))
+        })?;
+        ensure_libsql_column(
+            &conn,
+            "ALTER TABLE matter_tasks ADD COLUMN parent_task_id TEXT",
+        )
+        .await?;
+        ensure_libsql_column(
+            &conn,
+            "ALTER TABLE matter_tasks ADD COLUMN checklist TEXT NOT NULL DEFAULT '[]'",
+        )
+        .await?;
+        ensure_libsql_column(
+            &conn,
+            "ALTER TABLE agent_jobs ADD COLUMN priority TEXT NOT NULL DEFAULT 'normal'",
+        )
+        .await?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_matter_tasks_parent \
+             ON matter_tasks(user_id, matter_id, parent_task_id)",
+            (),
+        )
+        .await
+        .map_err(|e| {
+            DatabaseError::Migration(format!("failed to ensure matter subtask index: {}", e))
+        })?;
+        ensure_libsql_column(&conn, "ALTER TABLE matter_notes ADD COLUMN parent_id TEXT").await?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_matter_notes_parent \
+             ON matter_notes(user_id, matter_id, parent_id)",
+            (),
+        )
+        .await
+        .map_err(|e| {
+            DatabaseError::Migration(format!("failed to ensure matter note thread index: {}", e))
+        })?;
+        ensure_notification_mention_kind(&conn).await?;
+        ensure_libsql_column(
+            &conn,
+            "ALTER TABLE document_share_links \
+             ADD COLUMN failed_password_attempts INTEGER NOT NULL DEFAULT 0",
+        )
+        .await?;
+        ensure_libsql_column(
+            &conn,
+            "ALTER TABLE document_share_links ADD COLUMN last_password_failure_at TEXT",
+        )
+        .await?;
 
         // Deadline override audit log (idempotent; already in SCHEMA for fresh installs).
         conn.execute_batch(
@@ -657,6 +777,59 @@ async fn ensure_libsql_column(conn: &Connection, sql: &str) -> Result<(), Databa
     }
 }
 
+/// SQLite cannot alter a CHECK constraint, so databases created before the
+/// `mention` notification kind rebuild the table with the current schema.
+async fn ensure_notification_mention_kind(conn: &Connection) -> Result<(), DatabaseError> {
+    let mut rows = conn
+        .query(
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'notifications'",
+            (),
+        )
+        .await
+        .map_err(|e| DatabaseError::Migration(format!("failed to inspect notifications: {}", e)))?;
+    let current =
+        match rows.next().await.map_err(|e| {
+            DatabaseError::Migration(format!("failed to inspect notifications: {}", e))
+        })? {
+            Some(row) => get_text(&row, 0),
+            None => return Ok(()),
+        };
+    if current.contains("'mention'") {
+        return Ok(());
+    }
+    conn.execute_batch(
+        "BEGIN;
+        ALTER TABLE notifications RENAME TO notifications_pre_mention;
+        CREATE TABLE notifications (
+            id TEXT PRIMARY KEY,
+            user_id TEXT NOT NULL,
+            kind TEXT NOT NULL CHECK (kind IN ('routine', 'deadline', 'system', 'mention')),
+            severity TEXT NOT NULL DEFAULT 'info' CHECK (severity IN ('info', 'warn', 'critical')),
+            title TEXT NOT NULL,
+            body TEXT NOT NULL DEFAULT '',
+            matter_id TEXT,
+            source TEXT,
+            read_at TEXT,
+            acked_at TEXT,
+            snoozed_until TEXT,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+        INSERT INTO notifications
+            SELECT id, user_id, kind, severity, title, body, matter_id, source,
+                   read_at, acked_at, snoozed_until, created_at
+            FROM notifications_pre_mention;
+        DROP TABLE notifications_pre_mention;
+        CREATE INDEX IF NOT EXISTS idx_notifications_user_created
+            ON notifications(user_id, created_at);
+        COMMIT;",
+    )
+    .await
+    .map_err(|e| {
+        DatabaseError::Migration(format!("failed to add mention notification kind: {}", e))
+    })?;
+    Ok(())
+}
+
 // ==================== Row conversion helpers ====================
 
 pub(crate) fn row_to_memory_document(row: &libsql::Row) -> MemoryDocument {
@@ -698,6 +871,8 @@ pub(crate) fn row_to_routine_libsql(row: &libsql::Row) -> Result<Routine, Databa
             cooldown: std::time::Duration::from_secs(cooldown_secs as u64),
             max_concurrent: max_concurrent as u32,
             dedup_window: dedup_window_secs.map(|s| std::time::Duration::from_secs(s as u64)),
+            urgent: get_i64(row, 24) != 0,
+            execution: serde_json::from_value(get_json(row, 25)).unwrap_or_default(),
         },
         notify: NotifyConfig {
             channel: get_opt_text(row, 12),
@@ -706,6 +881,7 @@ pub(crate) fn row_to_routine_libsql(row: &libsql::Row) -> Result<Routine, Databa
             on_failure: get_i64(row, 15) != 0,
             on_attention: get_i64(row, 16) != 0,
         },
+        chain: serde_json::from_value(get_json(row, 26)).unwrap_or_default(),
         state: get_json(row, 17),
         last_run_at: get_opt_ts(row, 18),
         next_fire_at: get_opt_ts(row, 19),
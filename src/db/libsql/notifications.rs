@@ -0,0 +1,360 @@
+//! NotificationStore implementation for LibSqlBackend.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use libsql::params;
+use uuid::Uuid;
+
+use super::{LibSqlBackend, fmt_ts, get_i64, get_opt_text, get_opt_ts, get_text, get_ts, opt_text};
+use crate::db::{
+    AuditSeverity, NewNotificationParams, NotificationFilter, NotificationKind, NotificationRecord,
+    NotificationStore,
+};
+use crate::error::DatabaseError;
+
+const NOTIFICATION_COLUMNS: &str = "id, user_id, kind, severity, title, body, matter_id, \
+     source, read_at, acked_at, snoozed_until, created_at";
+
+fn row_to_notification(row: &libsql::Row) -> Result<NotificationRecord, DatabaseError> {
+    let kind_raw = get_text(row, 2);
+    let kind = NotificationKind::from_db_value(&kind_raw).ok_or_else(|| {
+        DatabaseError::Serialization(format!("invalid notification kind '{kind_raw}'"))
+    })?;
+    Ok(NotificationRecord {
+        id: get_text(row, 0)
+            .parse()
+            .map_err(|e: uuid::Error| DatabaseError::Serialization(e.to_string()))?,
+        user_id: get_text(row, 1),
+        kind,
+        severity: AuditSeverity::from_db_value(&get_text(row, 3)).unwrap_or(AuditSeverity::Info),
+        title: get_text(row, 4),
+        body: get_text(row, 5),
+        matter_id: get_opt_text(row, 6),
+        source: get_opt_text(row, 7),
+        read_at: get_opt_ts(row, 8),
+        acked_at: get_opt_ts(row, 9),
+        snoozed_until: get_opt_ts(row, 10),
+        created_at: get_ts(row, 11),
+    })
+}
+
+/// `WHERE` condition for `filter`; `?2` is bound to the current time.
+fn filter_condition(filter: NotificationFilter) -> &'static str {
+    match filter {
+        NotificationFilter::Active => {
+            "acked_at IS NULL AND (snoozed_until IS NULL OR snoozed_until <= ?2)"
+        }
+        NotificationFilter::Unread => {
+            "read_at IS NULL AND acked_at IS NULL \
+             AND (snoozed_until IS NULL OR snoozed_until <= ?2)"
+        }
+        NotificationFilter::Snoozed => "acked_at IS NULL AND snoozed_until > ?2",
+        NotificationFilter::Acked => "acked_at IS NOT NULL",
+        NotificationFilter::All => "1 = 1",
+    }
+}
+
+impl LibSqlBackend {
+    async fn query_notification(
+        &self,
+        sql: &str,
+        values: impl libsql::params::IntoParams,
+    ) -> Result<Option<NotificationRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(sql, values)
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        match rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            Some(row) => Ok(Some(row_to_notification(&row)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationStore for LibSqlBackend {
+    async fn create_notification(
+        &self,
+        params: &NewNotificationParams,
+    ) -> Result<NotificationRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+        conn.execute(
+            "INSERT INTO notifications \
+             (id, user_id, kind, severity, title, body, matter_id, source, created_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                id.to_string(),
+                params.user_id.as_str(),
+                params.kind.as_str(),
+                params.severity.as_str(),
+                params.title.as_str(),
+                params.body.as_str(),
+                opt_text(params.matter_id.as_deref()),
+                opt_text(params.source.as_deref()),
+                fmt_ts(&now),
+            ],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(NotificationRecord {
+            id,
+            user_id: params.user_id.clone(),
+            kind: params.kind,
+            severity: params.severity,
+            title: params.title.clone(),
+            body: params.body.clone(),
+            matter_id: params.matter_id.clone(),
+            source: params.source.clone(),
+            read_at: None,
+            acked_at: None,
+            snoozed_until: None,
+            created_at: now,
+        })
+    }
+
+    async fn list_notifications(
+        &self,
+        user_id: &str,
+        filter: NotificationFilter,
+        kind: Option<NotificationKind>,
+        limit: i64,
+    ) -> Result<Vec<NotificationRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {NOTIFICATION_COLUMNS} FROM notifications \
+                     WHERE user_id = ?1 AND {} \
+                       AND (?3 IS NULL OR kind = ?3) \
+                     ORDER BY created_at DESC LIMIT ?4",
+                    filter_condition(filter)
+                ),
+                params![
+                    user_id,
+                    fmt_ts(&Utc::now()),
+                    opt_text(kind.map(NotificationKind::as_str)),
+                    limit
+                ],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let mut notifications = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            notifications.push(row_to_notification(&row)?);
+        }
+        Ok(notifications)
+    }
+
+    async fn count_unread_notifications(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<(NotificationKind, i64)>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT kind, COUNT(*) FROM notifications \
+                     WHERE user_id = ?1 AND {} \
+                     GROUP BY kind ORDER BY kind",
+                    filter_condition(NotificationFilter::Unread)
+                ),
+                params![user_id, fmt_ts(&Utc::now())],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let mut counts = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            if let Some(kind) = NotificationKind::from_db_value(&get_text(&row, 0)) {
+                counts.push((kind, get_i64(&row, 1)));
+            }
+        }
+        Ok(counts)
+    }
+
+    async fn mark_notification_read(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<NotificationRecord>, DatabaseError> {
+        self.query_notification(
+            &format!(
+                "UPDATE notifications SET read_at = COALESCE(read_at, ?3) \
+                 WHERE user_id = ?1 AND id = ?2 RETURNING {NOTIFICATION_COLUMNS}"
+            ),
+            params![user_id, id.to_string(), fmt_ts(&Utc::now())],
+        )
+        .await
+    }
+
+    async fn mark_all_notifications_read(&self, user_id: &str) -> Result<u64, DatabaseError> {
+        let conn = self.connect().await?;
+        let now = fmt_ts(&Utc::now());
+        conn.execute(
+            "UPDATE notifications SET read_at = ?2 \
+             WHERE user_id = ?1 AND read_at IS NULL AND acked_at IS NULL \
+               AND (snoozed_until IS NULL OR snoozed_until <= ?2)",
+            params![user_id, now],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))
+    }
+
+    async fn ack_notification(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<NotificationRecord>, DatabaseError> {
+        self.query_notification(
+            &format!(
+                "UPDATE notifications SET read_at = COALESCE(read_at, ?3), \
+                   acked_at = COALESCE(acked_at, ?3), snoozed_until = NULL \
+                 WHERE user_id = ?1 AND id = ?2 RETURNING {NOTIFICATION_COLUMNS}"
+            ),
+            params![user_id, id.to_string(), fmt_ts(&Utc::now())],
+        )
+        .await
+    }
+
+    async fn snooze_notification(
+        &self,
+        user_id: &str,
+        id: Uuid,
+        until: DateTime<Utc>,
+    ) -> Result<Option<NotificationRecord>, DatabaseError> {
+        self.query_notification(
+            &format!(
+                "UPDATE notifications SET snoozed_until = ?3, read_at = NULL \
+                 WHERE user_id = ?1 AND id = ?2 AND acked_at IS NULL \
+                 RETURNING {NOTIFICATION_COLUMNS}"
+            ),
+            params![user_id, id.to_string(), fmt_ts(&until)],
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::db::{AuditSeverity, NewNotificationParams, NotificationFilter, NotificationKind};
+
+    fn notification(kind: NotificationKind, title: &str) -> NewNotificationParams {
+        NewNotificationParams {
+            user_id: "default".to_string(),
+            kind,
+            severity: AuditSeverity::Info,
+            title: title.to_string(),
+            body: String::new(),
+            matter_id: None,
+            source: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn inbox_tracks_read_ack_and_snooze() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let digest = db
+            .create_notification(&notification(NotificationKind::Routine, "Morning digest"))
+            .await
+            .unwrap();
+        let deadline = db
+            .create_notification(&notification(NotificationKind::Deadline, "Answer due"))
+            .await
+            .unwrap();
+        let warning = db
+            .create_notification(&notification(NotificationKind::System, "Job dead-lettered"))
+            .await
+            .unwrap();
+
+        let unread = db.count_unread_notifications("default").await.unwrap();
+        assert_eq!(unread.iter().map(|(_, n)| n).sum::<i64>(), 3);
+        assert!(
+            db.count_unread_notifications("other")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let read = db
+            .mark_notification_read("default", digest.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(read.read_at.is_some());
+        db.ack_notification("default", warning.id).await.unwrap();
+        db.snooze_notification("default", deadline.id, Utc::now() + Duration::hours(1))
+            .await
+            .unwrap();
+        assert!(
+            db.count_unread_notifications("default")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let titles = |list: Vec<crate::db::NotificationRecord>| {
+            list.into_iter().map(|n| n.title).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            titles(
+                db.list_notifications("default", NotificationFilter::Active, None, 50)
+                    .await
+                    .unwrap()
+            ),
+            vec!["Morning digest"]
+        );
+        assert_eq!(
+            titles(
+                db.list_notifications("default", NotificationFilter::Snoozed, None, 50)
+                    .await
+                    .unwrap()
+            ),
+            vec!["Answer due"]
+        );
+        assert_eq!(
+            titles(
+                db.list_notifications(
+                    "default",
+                    NotificationFilter::All,
+                    Some(NotificationKind::System),
+                    50
+                )
+                .await
+                .unwrap()
+            ),
+            vec!["Job dead-lettered"]
+        );
+
+        // An elapsed snooze puts the notification back in the unread count.
+        db.snooze_notification("default", deadline.id, Utc::now() - Duration::seconds(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            db.count_unread_notifications("default").await.unwrap(),
+            vec![(NotificationKind::Deadline, 1)]
+        );
+        assert_eq!(db.mark_all_notifications_read("default").await.unwrap(), 1);
+        assert!(
+            db.ack_notification("other", deadline.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
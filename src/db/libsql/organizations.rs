@@ -0,0 +1,579 @@
+//! OrganizationStore implementation for LibSqlBackend.
+
+use async_trait::async_trait;
+use libsql::params;
+use uuid::Uuid;
+
+use super::legal_practice::row_to_client_record;
+use super::{LibSqlBackend, get_text, get_ts};
+use crate::db::{
+    ClientRecord, OrgRole, OrganizationMatterRecord, OrganizationMemberRecord, OrganizationRecord,
+    OrganizationStore,
+};
+use crate::error::DatabaseError;
+
+const ORGANIZATION_COLUMNS: &str = "o.id, o.name, o.created_by, o.created_at, o.updated_at";
+
+const MEMBER_COLUMNS: &str = "org_id, user_id, role, created_at, updated_at";
+
+fn parse_org_id(raw: &str) -> Result<Uuid, DatabaseError> {
+    raw.parse::<Uuid>()
+        .map_err(|e| DatabaseError::Serialization(format!("invalid organization id '{raw}': {e}")))
+}
+
+fn parse_org_role(raw: &str) -> Result<OrgRole, DatabaseError> {
+    OrgRole::from_db_value(raw)
+        .ok_or_else(|| DatabaseError::Serialization(format!("invalid organization role '{raw}'")))
+}
+
+fn row_to_organization(row: &libsql::Row) -> Result<OrganizationRecord, DatabaseError> {
+    Ok(OrganizationRecord {
+        id: parse_org_id(&get_text(row, 0))?,
+        name: get_text(row, 1),
+        created_by: get_text(row, 2),
+        created_at: get_ts(row, 3),
+        updated_at: get_ts(row, 4),
+    })
+}
+
+fn row_to_member(row: &libsql::Row) -> Result<OrganizationMemberRecord, DatabaseError> {
+    Ok(OrganizationMemberRecord {
+        org_id: parse_org_id(&get_text(row, 0))?,
+        user_id: get_text(row, 1),
+        role: parse_org_role(&get_text(row, 2))?,
+        created_at: get_ts(row, 3),
+        updated_at: get_ts(row, 4),
+    })
+}
+
+/// Guard shared by member demotions and removals (`?1` org, `?2` user): the
+/// member is not an owner, or another owner remains. Evaluated inside the
+/// write statement, so concurrent demotions cannot both pass.
+const KEEPS_AN_OWNER: &str = "organization_members.role <> 'owner' \
+     OR EXISTS (SELECT 1 FROM organization_members o \
+     WHERE o.org_id = ?1 AND o.role = 'owner' AND o.user_id <> ?2)";
+
+fn last_owner_error() -> DatabaseError {
+    DatabaseError::Constraint("organization must keep at least one owner".to_string())
+}
+
+fn org_id_value(org_id: Option<Uuid>) -> libsql::Value {
+    match org_id {
+        Some(id) => libsql::Value::Text(id.to_string()),
+        None => libsql::Value::Null,
+    }
+}
+
+#[async_trait]
+impl OrganizationStore for LibSqlBackend {
+    async fn create_organization(
+        &self,
+        name: &str,
+        created_by: &str,
+    ) -> Result<OrganizationRecord, DatabaseError> {
+        let id = Uuid::new_v4();
+        let conn = self.connect().await?;
+        conn.execute("BEGIN", ()).await?;
+
+        let op_result: Result<(), DatabaseError> = async {
+            conn.execute(
+                "INSERT INTO organizations (id, name, created_by) VALUES (?1, ?2, ?3)",
+                params![id.to_string(), name, created_by],
+            )
+            .await?;
+            conn.execute(
+                "INSERT INTO organization_members (org_id, user_id, role) VALUES (?1, ?2, ?3)",
+                params![id.to_string(), created_by, OrgRole::Owner.as_str()],
+            )
+            .await?;
+            Ok(())
+        }
+        .await;
+
+        match op_result {
+            Ok(()) => conn.execute("COMMIT", ()).await?,
+            Err(err) => {
+                let _ = conn.execute("ROLLBACK", ()).await;
+                return Err(err);
+            }
+        };
+        self.get_organization(id)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound {
+                entity: "organization".to_string(),
+                id: id.to_string(),
+            })
+    }
+
+    async fn get_organization(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Option<OrganizationRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let row = conn
+            .query(
+                &format!("SELECT {ORGANIZATION_COLUMNS} FROM organizations o WHERE o.id = ?1"),
+                params![org_id.to_string()],
+            )
+            .await?
+            .next()
+            .await?;
+        row.map(|row| row_to_organization(&row)).transpose()
+    }
+
+    async fn list_organizations_for_user(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<(OrganizationRecord, OrgRole)>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {ORGANIZATION_COLUMNS}, om.role FROM organizations o \
+                     JOIN organization_members om ON om.org_id = o.id \
+                     WHERE om.user_id = ?1 ORDER BY o.name, o.id"
+                ),
+                params![user_id],
+            )
+            .await?;
+        let mut out = Vec::new();
+        while let Some(row) = rows.next().await? {
+            out.push((
+                row_to_organization(&row)?,
+                parse_org_role(&get_text(&row, 5))?,
+            ));
+        }
+        Ok(out)
+    }
+
+    async fn upsert_organization_member(
+        &self,
+        org_id: Uuid,
+        user_id: &str,
+        role: OrgRole,
+    ) -> Result<OrganizationMemberRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        let row = conn
+            .query(
+                &format!(
+                    "INSERT INTO organization_members (org_id, user_id, role) \
+                     VALUES (?1, ?2, ?3) \
+                     ON CONFLICT (org_id, user_id) DO UPDATE SET \
+                     role = excluded.role, updated_at = datetime('now') \
+                     WHERE excluded.role = 'owner' OR {KEEPS_AN_OWNER} \
+                     RETURNING {MEMBER_COLUMNS}"
+                ),
+                params![org_id.to_string(), user_id, role.as_str()],
+            )
+            .await?
+            .next()
+            .await?
+            .ok_or_else(last_owner_error)?;
+        row_to_member(&row)
+    }
+
+    async fn list_organization_members(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Vec<OrganizationMemberRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {MEMBER_COLUMNS} FROM organization_members \
+                     WHERE org_id = ?1 ORDER BY user_id"
+                ),
+                params![org_id.to_string()],
+            )
+            .await?;
+        let mut out = Vec::new();
+        while let Some(row) = rows.next().await? {
+            out.push(row_to_member(&row)?);
+        }
+        Ok(out)
+    }
+
+    async fn get_organization_role(
+        &self,
+        org_id: Uuid,
+        user_id: &str,
+    ) -> Result<Option<OrgRole>, DatabaseError> {
+        let conn = self.connect().await?;
+        let row = conn
+            .query(
+                "SELECT role FROM organization_members WHERE org_id = ?1 AND user_id = ?2",
+                params![org_id.to_string(), user_id],
+            )
+            .await?
+            .next()
+            .await?;
+        row.map(|row| parse_org_role(&get_text(&row, 0)))
+            .transpose()
+    }
+
+    async fn remove_organization_member(
+        &self,
+        org_id: Uuid,
+        user_id: &str,
+    ) -> Result<bool, DatabaseError> {
+        let conn = self.connect().await?;
+        let removed = conn
+            .execute(
+                &format!(
+                    "DELETE FROM organization_members \
+                     WHERE org_id = ?1 AND user_id = ?2 AND ({KEEPS_AN_OWNER})"
+                ),
+                params![org_id.to_string(), user_id],
+            )
+            .await?;
+        if removed > 0 {
+            return Ok(true);
+        }
+        match self.get_organization_role(org_id, user_id).await? {
+            Some(_) => Err(last_owner_error()),
+            None => Ok(false),
+        }
+    }
+
+    async fn set_matter_organization(
+        &self,
+        matter_owner_user_id: &str,
+        matter_id: &str,
+        org_id: Option<Uuid>,
+    ) -> Result<bool, DatabaseError> {
+        let conn = self.connect().await?;
+        let updated = conn
+            .execute(
+                "UPDATE matters SET org_id = ?3, updated_at = datetime('now') \
+                 WHERE user_id = ?1 AND matter_id = ?2",
+                params![matter_owner_user_id, matter_id, org_id_value(org_id)],
+            )
+            .await?;
+        Ok(updated > 0)
+    }
+
+    async fn get_matter_organization(
+        &self,
+        matter_owner_user_id: &str,
+        matter_id: &str,
+    ) -> Result<Option<Uuid>, DatabaseError> {
+        let conn = self.connect().await?;
+        let row = conn
+            .query(
+                "SELECT org_id FROM matters \
+                 WHERE user_id = ?1 AND matter_id = ?2 AND org_id IS NOT NULL",
+                params![matter_owner_user_id, matter_id],
+            )
+            .await?
+            .next()
+            .await?;
+        row.map(|row| parse_org_id(&get_text(&row, 0))).transpose()
+    }
+
+    async fn set_client_organization(
+        &self,
+        user_id: &str,
+        client_id: Uuid,
+        org_id: Option<Uuid>,
+    ) -> Result<bool, DatabaseError> {
+        let conn = self.connect().await?;
+        let updated = conn
+            .execute(
+                "UPDATE clients SET org_id = ?3, updated_at = datetime('now') \
+                 WHERE user_id = ?1 AND id = ?2",
+                params![user_id, client_id.to_string(), org_id_value(org_id)],
+            )
+            .await?;
+        Ok(updated > 0)
+    }
+
+    async fn set_documents_organization(
+        &self,
+        user_id: &str,
+        path_prefix: &str,
+        org_id: Option<Uuid>,
+    ) -> Result<u64, DatabaseError> {
+        let conn = self.connect().await?;
+        let updated = conn
+            .execute(
+                "UPDATE memory_documents SET org_id = ?3 \
+                 WHERE user_id = ?1 AND substr(path, 1, length(?2)) = ?2",
+                params![user_id, path_prefix, org_id_value(org_id)],
+            )
+            .await?;
+        Ok(updated)
+    }
+
+    async fn list_organization_matters(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Vec<OrganizationMatterRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                "SELECT user_id, matter_id FROM matters WHERE org_id = ?1 \
+                 ORDER BY matter_id, user_id",
+                params![org_id.to_string()],
+            )
+            .await?;
+        let mut out = Vec::new();
+        while let Some(row) = rows.next().await? {
+            out.push(OrganizationMatterRecord {
+                org_id,
+                matter_owner_user_id: get_text(&row, 0),
+                matter_id: get_text(&row, 1),
+            });
+        }
+        Ok(out)
+    }
+
+    async fn list_organization_clients(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Vec<ClientRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                "SELECT id, user_id, name, name_normalized, client_type, email, phone, address, \
+                 notes, created_at, updated_at \
+                 FROM clients WHERE org_id = ?1 ORDER BY name_normalized, id",
+                params![org_id.to_string()],
+            )
+            .await?;
+        let mut out = Vec::new();
+        while let Some(row) = rows.next().await? {
+            out.push(row_to_client_record(&row)?);
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::libsql::LibSqlBackend;
+    use crate::db::{
+        ClientStore, ClientType, CreateClientParams, Database, MatterMemberRole, MatterStatus,
+        MatterStore, OrgRole, OrganizationStore, RbacStore, UpsertMatterMembershipParams,
+        UpsertMatterParams, UserRole,
+    };
+    use crate::error::DatabaseError;
+
+    #[tokio::test]
+    async fn org_membership_grants_access_to_shared_matters() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let backend = LibSqlBackend::new_local(&dir.path().join("orgs.db"))
+            .await
+            .expect("backend");
+        backend.run_migrations().await.expect("migrations");
+        for id in ["owner", "colleague", "reader"] {
+            backend
+                .ensure_user_account(id, id, UserRole::Attorney)
+                .await
+                .expect("user");
+        }
+
+        let client = backend
+            .create_client(
+                "owner",
+                &CreateClientParams {
+                    name: "Acme".to_string(),
+                    client_type: ClientType::Entity,
+                    email: None,
+                    phone: None,
+                    address: None,
+                    notes: None,
+                },
+            )
+            .await
+            .expect("client");
+        backend
+            .upsert_matter(
+                "owner",
+                &UpsertMatterParams {
+                    matter_id: "acme-v-foo".to_string(),
+                    client_id: client.id,
+                    status: MatterStatus::Active,
+                    stage: None,
+                    practice_area: None,
+                    jurisdiction: None,
+                    opened_at: None,
+                    closed_at: None,
+                    assigned_to: Vec::new(),
+                    custom_fields: serde_json::json!({}),
+                },
+            )
+            .await
+            .expect("matter");
+
+        let org = backend
+            .create_organization("Firm", "owner")
+            .await
+            .expect("org");
+        assert_eq!(
+            backend
+                .get_organization_role(org.id, "owner")
+                .await
+                .expect("role"),
+            Some(OrgRole::Owner)
+        );
+        backend
+            .upsert_organization_member(org.id, "colleague", OrgRole::Member)
+            .await
+            .expect("member");
+        backend
+            .upsert_organization_member(org.id, "reader", OrgRole::Viewer)
+            .await
+            .expect("viewer");
+
+        assert_eq!(
+            backend
+                .check_matter_access("owner", "acme-v-foo", "colleague")
+                .await
+                .expect("access"),
+            None
+        );
+        assert!(
+            backend
+                .set_matter_organization("owner", "acme-v-foo", Some(org.id))
+                .await
+                .expect("share")
+        );
+        assert!(
+            backend
+                .set_client_organization("owner", client.id, Some(org.id))
+                .await
+                .expect("share client")
+        );
+        assert_eq!(
+            backend
+                .check_matter_access("owner", "acme-v-foo", "colleague")
+                .await
+                .expect("access"),
+            Some(MatterMemberRole::Collaborator)
+        );
+        assert_eq!(
+            backend
+                .check_matter_access("owner", "acme-v-foo", "reader")
+                .await
+                .expect("access"),
+            Some(MatterMemberRole::Viewer)
+        );
+
+        // A matter membership and an org role combine to the stronger one.
+        for (member, role) in [
+            ("colleague", MatterMemberRole::Viewer),
+            ("reader", MatterMemberRole::Collaborator),
+        ] {
+            backend
+                .upsert_matter_membership(&UpsertMatterMembershipParams {
+                    matter_owner_user_id: "owner".to_string(),
+                    matter_id: "acme-v-foo".to_string(),
+                    member_user_id: member.to_string(),
+                    role,
+                })
+                .await
+                .expect("matter membership");
+            assert_eq!(
+                backend
+                    .check_matter_access("owner", "acme-v-foo", member)
+                    .await
+                    .expect("access"),
+                Some(MatterMemberRole::Collaborator),
+                "{member}"
+            );
+        }
+        backend
+            .remove_matter_membership("owner", "acme-v-foo", "colleague")
+            .await
+            .expect("remove matter membership");
+
+        let matters = backend
+            .list_organization_matters(org.id)
+            .await
+            .expect("matters");
+        assert_eq!(matters.len(), 1);
+        assert_eq!(matters[0].matter_owner_user_id, "owner");
+        let clients = backend
+            .list_organization_clients(org.id)
+            .await
+            .expect("clients");
+        assert_eq!(clients.len(), 1);
+
+        assert!(
+            backend
+                .remove_organization_member(org.id, "colleague")
+                .await
+                .expect("remove")
+        );
+        assert_eq!(
+            backend
+                .check_matter_access("owner", "acme-v-foo", "colleague")
+                .await
+                .expect("access"),
+            None
+        );
+        let orgs = backend
+            .list_organizations_for_user("reader")
+            .await
+            .expect("orgs");
+        assert_eq!(orgs.len(), 1);
+        assert_eq!(orgs[0].1, OrgRole::Viewer);
+    }
+
+    #[tokio::test]
+    async fn last_owner_cannot_be_demoted_or_removed() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let backend = LibSqlBackend::new_local(&dir.path().join("orgs.db"))
+            .await
+            .expect("backend");
+        backend.run_migrations().await.expect("migrations");
+        for id in ["owner", "partner"] {
+            backend
+                .ensure_user_account(id, id, UserRole::Attorney)
+                .await
+                .expect("user");
+        }
+        let org = backend
+            .create_organization("Firm", "owner")
+            .await
+            .expect("org");
+
+        let err = backend
+            .upsert_organization_member(org.id, "owner", OrgRole::Admin)
+            .await
+            .expect_err("demoting the last owner");
+        assert!(matches!(err, DatabaseError::Constraint(_)), "{err:?}");
+        let err = backend
+            .remove_organization_member(org.id, "owner")
+            .await
+            .expect_err("removing the last owner");
+        assert!(matches!(err, DatabaseError::Constraint(_)), "{err:?}");
+        assert!(
+            !backend
+                .remove_organization_member(org.id, "stranger")
+                .await
+                .expect("remove non-member")
+        );
+
+        backend
+            .upsert_organization_member(org.id, "partner", OrgRole::Owner)
+            .await
+            .expect("second owner");
+        let demoted = backend
+            .upsert_organization_member(org.id, "owner", OrgRole::Member)
+            .await
+            .expect("demote with another owner left");
+        assert_eq!(demoted.role, OrgRole::Member);
+        assert!(
+            backend
+                .remove_organization_member(org.id, "owner")
+                .await
+                .expect("remove")
+        );
+        let err = backend
+            .remove_organization_member(org.id, "partner")
+            .await
+            .expect_err("removing the new last owner");
+        assert!(matches!(err, DatabaseError::Constraint(_)), "{err:?}");
+    }
+}
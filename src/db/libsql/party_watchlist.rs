@@ -0,0 +1,162 @@
+//! PartyWatchlistStore implementation for LibSqlBackend.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use libsql::params;
+use uuid::Uuid;
+
+use super::{LibSqlBackend, fmt_ts, get_opt_text, get_text, get_ts, opt_text};
+use crate::db::{
+    NewWatchlistEntryParams, PartyWatchlistStore, WatchlistCategory, WatchlistEntryRecord,
+    normalize_party_name,
+};
+use crate::error::DatabaseError;
+
+const WATCHLIST_COLUMNS: &str =
+    "id, user_id, name, name_normalized, category, reason, created_by, created_at";
+
+fn row_to_watchlist_entry(row: &libsql::Row) -> Result<WatchlistEntryRecord, DatabaseError> {
+    let category_raw = get_text(row, 4);
+    Ok(WatchlistEntryRecord {
+        id: get_text(row, 0)
+            .parse()
+            .map_err(|e: uuid::Error| DatabaseError::Serialization(e.to_string()))?,
+        user_id: get_text(row, 1),
+        name: get_text(row, 2),
+        name_normalized: get_text(row, 3),
+        category: WatchlistCategory::from_db_value(&category_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("unknown watchlist category '{category_raw}'"))
+        })?,
+        reason: get_opt_text(row, 5),
+        created_by: get_text(row, 6),
+        created_at: get_ts(row, 7),
+    })
+}
+
+#[async_trait]
+impl PartyWatchlistStore for LibSqlBackend {
+    async fn upsert_watchlist_entry(
+        &self,
+        params: &NewWatchlistEntryParams,
+    ) -> Result<WatchlistEntryRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "INSERT INTO party_watchlist \
+                     (id, user_id, name, name_normalized, category, reason, created_by, created_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8) \
+                     ON CONFLICT(user_id, name_normalized) DO UPDATE SET \
+                       name = excluded.name, category = excluded.category, \
+                       reason = excluded.reason \
+                     RETURNING {WATCHLIST_COLUMNS}"
+                ),
+                params![
+                    Uuid::new_v4().to_string(),
+                    params.user_id.as_str(),
+                    params.name.as_str(),
+                    normalize_party_name(&params.name),
+                    params.category.as_str(),
+                    opt_text(params.reason.as_deref()),
+                    params.created_by.as_str(),
+                    fmt_ts(&Utc::now()),
+                ],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let row = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+            .ok_or_else(|| DatabaseError::Query("watchlist upsert returned no row".to_string()))?;
+        row_to_watchlist_entry(&row)
+    }
+
+    async fn list_watchlist_entries(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<WatchlistEntryRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {WATCHLIST_COLUMNS} FROM party_watchlist \
+                     WHERE user_id = ?1 ORDER BY name_normalized"
+                ),
+                params![user_id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let mut entries = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            entries.push(row_to_watchlist_entry(&row)?);
+        }
+        Ok(entries)
+    }
+
+    async fn delete_watchlist_entry(&self, user_id: &str, id: Uuid) -> Result<bool, DatabaseError> {
+        let conn = self.connect().await?;
+        let deleted = conn
+            .execute(
+                "DELETE FROM party_watchlist WHERE user_id = ?1 AND id = ?2",
+                params![user_id, id.to_string()],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(deleted > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::{NewWatchlistEntryParams, WatchlistCategory};
+
+    fn entry(name: &str, category: WatchlistCategory) -> NewWatchlistEntryParams {
+        NewWatchlistEntryParams {
+            user_id: "default".to_string(),
+            name: name.to_string(),
+            category,
+            reason: None,
+            created_by: "default".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn watchlist_upserts_by_normalized_name() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let first = db
+            .upsert_watchlist_entry(&entry("Globex Corp.", WatchlistCategory::AdverseParty))
+            .await
+            .unwrap();
+        assert_eq!(first.name_normalized, "globex corp");
+        let again = db
+            .upsert_watchlist_entry(&NewWatchlistEntryParams {
+                reason: Some("OFAC listing".to_string()),
+                ..entry("GLOBEX CORP", WatchlistCategory::Sanctioned)
+            })
+            .await
+            .unwrap();
+        assert_eq!(again.id, first.id);
+        assert_eq!(again.category, WatchlistCategory::Sanctioned);
+        assert_eq!(again.reason.as_deref(), Some("OFAC listing"));
+
+        db.upsert_watchlist_entry(&entry("Initech", WatchlistCategory::Other))
+            .await
+            .unwrap();
+        let listed = db.list_watchlist_entries("default").await.unwrap();
+        assert_eq!(listed.len(), 2);
+        assert!(db.list_watchlist_entries("other").await.unwrap().is_empty());
+
+        assert!(!db.delete_watchlist_entry("other", first.id).await.unwrap());
+        assert!(
+            db.delete_watchlist_entry("default", first.id)
+                .await
+                .unwrap()
+        );
+        assert_eq!(db.list_watchlist_entries("default").await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,146 @@
+//! PrecedentStore implementation for LibSqlBackend.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use libsql::params;
+use uuid::Uuid;
+
+use super::{LibSqlBackend, fmt_ts, get_opt_text, get_text, get_ts, opt_text};
+use crate::db::{CreatePrecedentParams, PrecedentRecord, PrecedentStore};
+use crate::error::DatabaseError;
+
+const PRECEDENT_COLUMNS: &str = "id, user_id, title, path, source_matter_id, source_path, \
+     document_type, practice_area, jurisdiction, promoted_by, created_at";
+
+fn row_to_precedent(row: &libsql::Row) -> Result<PrecedentRecord, DatabaseError> {
+    Ok(PrecedentRecord {
+        id: get_text(row, 0)
+            .parse()
+            .map_err(|e: uuid::Error| DatabaseError::Serialization(e.to_string()))?,
+        user_id: get_text(row, 1),
+        title: get_text(row, 2),
+        path: get_text(row, 3),
+        source_matter_id: get_text(row, 4),
+        source_path: get_text(row, 5),
+        document_type: get_opt_text(row, 6),
+        practice_area: get_opt_text(row, 7),
+        jurisdiction: get_opt_text(row, 8),
+        promoted_by: get_text(row, 9),
+        created_at: get_ts(row, 10),
+    })
+}
+
+#[async_trait]
+impl PrecedentStore for LibSqlBackend {
+    async fn create_precedent(
+        &self,
+        user_id: &str,
+        input: &CreatePrecedentParams,
+    ) -> Result<PrecedentRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "INSERT INTO precedents \
+                     (id, user_id, title, path, source_matter_id, source_path, \
+                      document_type, practice_area, jurisdiction, promoted_by, created_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11) \
+                     RETURNING {PRECEDENT_COLUMNS}"
+                ),
+                params![
+                    Uuid::new_v4().to_string(),
+                    user_id,
+                    input.title.as_str(),
+                    input.path.as_str(),
+                    input.source_matter_id.as_str(),
+                    input.source_path.as_str(),
+                    opt_text(input.document_type.as_deref()),
+                    opt_text(input.practice_area.as_deref()),
+                    opt_text(input.jurisdiction.as_deref()),
+                    input.promoted_by.as_str(),
+                    fmt_ts(&Utc::now()),
+                ],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let row = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+            .ok_or_else(|| DatabaseError::Query("precedent insert returned no row".to_string()))?;
+        row_to_precedent(&row)
+    }
+
+    async fn list_precedents(&self, user_id: &str) -> Result<Vec<PrecedentRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {PRECEDENT_COLUMNS} FROM precedents \
+                     WHERE user_id = ?1 ORDER BY created_at DESC, title"
+                ),
+                params![user_id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let mut precedents = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            precedents.push(row_to_precedent(&row)?);
+        }
+        Ok(precedents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::CreatePrecedentParams;
+
+    fn precedent(title: &str, path: &str, matter_id: &str) -> CreatePrecedentParams {
+        CreatePrecedentParams {
+            title: title.to_string(),
+            path: path.to_string(),
+            source_matter_id: matter_id.to_string(),
+            source_path: format!("matters/{matter_id}/drafts/agreement.md"),
+            document_type: Some("share purchase agreement".to_string()),
+            practice_area: Some("corporate".to_string()),
+            jurisdiction: None,
+            promoted_by: "default".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn precedents_are_listed_per_user_and_paths_are_unique() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let spa = db
+            .create_precedent(
+                "default",
+                &precedent("SPA", "precedent/spa.md", "acme-sale"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(spa.source_matter_id, "acme-sale");
+        assert_eq!(spa.practice_area.as_deref(), Some("corporate"));
+        assert!(
+            db.create_precedent(
+                "default",
+                &precedent("SPA again", "precedent/spa.md", "other")
+            )
+            .await
+            .is_err()
+        );
+        db.create_precedent(
+            "someone-else",
+            &precedent("SPA", "precedent/spa.md", "acme-sale"),
+        )
+        .await
+        .unwrap();
+
+        let listed = db.list_precedents("default").await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, spa.id);
+    }
+}
@@ -32,13 +32,13 @@ impl RoutineStore for LibSqlBackend {
                     trigger_type, trigger_config, action_type, action_config,
                     cooldown_secs, max_concurrent, dedup_window_secs,
                     notify_channel, notify_user, notify_on_success, notify_on_failure, notify_on_attention,
-                    state, next_fire_at, created_at, updated_at
+                    state, next_fire_at, created_at, updated_at, urgent, execution_override, chain
                 ) VALUES (
                     ?1, ?2, ?3, ?4, ?5,
                     ?6, ?7, ?8, ?9,
                     ?10, ?11, ?12,
                     ?13, ?14, ?15, ?16, ?17,
-                    ?18, ?19, ?20, ?21
+                    ?18, ?19, ?20, ?21, ?22, ?23, ?24
                 )
                 "#,
                 params![
@@ -63,6 +63,9 @@ impl RoutineStore for LibSqlBackend {
                     fmt_opt_ts(&routine.next_fire_at),
                     fmt_ts(&routine.created_at),
                     fmt_ts(&routine.updated_at),
+                    routine.guardrails.urgent as i64,
+                    routine.guardrails.execution_json().to_string(),
+                    routine.chain.to_json().to_string(),
                 ],
             )
             .await
@@ -211,7 +214,7 @@ impl RoutineStore for LibSqlBackend {
                     notify_channel = ?12, notify_user = ?13,
                     notify_on_success = ?14, notify_on_failure = ?15, notify_on_attention = ?16,
                     state = ?17, next_fire_at = ?18,
-                    updated_at = ?19
+                    updated_at = ?19, urgent = ?20, execution_override = ?21, chain = ?22
                 WHERE id = ?1
                 "#,
             params![
@@ -234,6 +237,9 @@ impl RoutineStore for LibSqlBackend {
                 routine.state.to_string(),
                 fmt_opt_ts(&routine.next_fire_at),
                 now,
+                routine.guardrails.urgent as i64,
+                routine.guardrails.execution_json().to_string(),
+                routine.chain.to_json().to_string(),
             ],
         )
         .await
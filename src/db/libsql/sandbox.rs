@@ -9,7 +9,7 @@ use super::{
     LibSqlBackend, fmt_opt_ts, fmt_ts, get_i64, get_json, get_opt_bool, get_opt_text, get_opt_ts,
     get_text, get_ts, opt_text,
 };
-use crate::db::SandboxStore;
+use crate::db::{JobArtifactRecord, SandboxStore};
 use crate::error::DatabaseError;
 use crate::history::{JobEventRecord, SandboxJobRecord, SandboxJobSummary};
 
@@ -167,7 +167,7 @@ impl SandboxStore for LibSqlBackend {
                     status = 'interrupted',
                     failure_reason = 'Process restarted',
                     completed_at = ?1
-                WHERE source = 'sandbox' AND status IN ('running', 'creating')
+                WHERE source = 'sandbox' AND status IN ('running', 'creating', 'queued')
                 "#,
                 params![now],
             )
@@ -199,10 +199,10 @@ impl SandboxStore for LibSqlBackend {
             let count = get_i64(&row, 1) as usize;
             summary.total += count;
             match status.as_str() {
-                "creating" => summary.creating += count,
+                "creating" | "queued" => summary.creating += count,
                 "running" => summary.running += count,
                 "completed" => summary.completed += count,
-                "failed" => summary.failed += count,
+                "failed" | "dead_letter" => summary.failed += count,
                 "interrupted" => summary.interrupted += count,
                 _ => {}
             }
@@ -274,10 +274,10 @@ impl SandboxStore for LibSqlBackend {
             let count = get_i64(&row, 1) as usize;
             summary.total += count;
             match status.as_str() {
-                "creating" => summary.creating += count,
+                "creating" | "queued" => summary.creating += count,
                 "running" => summary.running += count,
                 "completed" => summary.completed += count,
-                "failed" => summary.failed += count,
+                "failed" | "dead_letter" => summary.failed += count,
                 "interrupted" => summary.interrupted += count,
                 _ => {}
             }
@@ -336,6 +336,41 @@ impl SandboxStore for LibSqlBackend {
         }
     }
 
+    async fn update_sandbox_job_priority(
+        &self,
+        id: Uuid,
+        priority: &str,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute(
+            "UPDATE agent_jobs SET priority = ?2 WHERE id = ?1",
+            params![id.to_string(), priority],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_sandbox_job_priority(&self, id: Uuid) -> Result<Option<String>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                "SELECT priority FROM agent_jobs WHERE id = ?1",
+                params![id.to_string()],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+
+        match rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            Some(row) => Ok(Some(get_text(&row, 0))),
+            None => Ok(None),
+        }
+    }
+
     async fn save_job_event(
         &self,
         job_id: Uuid,
@@ -402,4 +437,163 @@ impl SandboxStore for LibSqlBackend {
         }
         Ok(events)
     }
+
+    async fn list_job_events_after(
+        &self,
+        job_id: Uuid,
+        after_id: i64,
+        limit: i64,
+    ) -> Result<Vec<JobEventRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                r#"
+                SELECT id, job_id, event_type, data, created_at
+                FROM job_events WHERE job_id = ?1 AND id > ?2
+                ORDER BY id ASC
+                LIMIT ?3
+                "#,
+                params![job_id.to_string(), after_id, limit],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+
+        let mut events = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            events.push(JobEventRecord {
+                id: get_i64(&row, 0),
+                job_id: get_text(&row, 1).parse().unwrap_or_default(),
+                event_type: get_text(&row, 2),
+                data: get_json(&row, 3),
+                created_at: get_ts(&row, 4),
+            });
+        }
+        Ok(events)
+    }
+
+    async fn save_job_artifact(&self, artifact: &JobArtifactRecord) -> Result<(), DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute(
+            r#"
+                INSERT INTO job_artifacts (
+                    job_id, name, size_bytes, content_type, storage_path, promoted_to, created_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                ON CONFLICT (job_id, name) DO UPDATE SET
+                    size_bytes = excluded.size_bytes,
+                    content_type = excluded.content_type,
+                    storage_path = excluded.storage_path,
+                    promoted_to = excluded.promoted_to,
+                    created_at = excluded.created_at
+                "#,
+            params![
+                artifact.job_id.to_string(),
+                artifact.name.as_str(),
+                artifact.size_bytes,
+                artifact.content_type.as_str(),
+                artifact.storage_path.as_str(),
+                opt_text(artifact.promoted_to.as_deref()),
+                fmt_ts(&artifact.created_at),
+            ],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn list_job_artifacts(
+        &self,
+        job_id: Uuid,
+    ) -> Result<Vec<JobArtifactRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let rows = conn
+            .query(
+                r#"
+                SELECT job_id, name, size_bytes, content_type, storage_path, promoted_to, created_at
+                FROM job_artifacts WHERE job_id = ?1 ORDER BY name
+                "#,
+                params![job_id.to_string()],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        collect_job_artifacts(rows).await
+    }
+
+    async fn get_job_artifact(
+        &self,
+        job_id: Uuid,
+        name: &str,
+    ) -> Result<Option<JobArtifactRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let rows = conn
+            .query(
+                r#"
+                SELECT job_id, name, size_bytes, content_type, storage_path, promoted_to, created_at
+                FROM job_artifacts WHERE job_id = ?1 AND name = ?2
+                "#,
+                params![job_id.to_string(), name],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(collect_job_artifacts(rows).await?.into_iter().next())
+    }
+
+    async fn mark_job_artifact_promoted(
+        &self,
+        job_id: Uuid,
+        name: &str,
+        promoted_to: &str,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute(
+            "UPDATE job_artifacts SET promoted_to = ?3 WHERE job_id = ?1 AND name = ?2",
+            params![job_id.to_string(), name, promoted_to],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn delete_job_artifacts_before(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<JobArtifactRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let rows = conn
+            .query(
+                r#"
+                DELETE FROM job_artifacts WHERE created_at < ?1
+                RETURNING job_id, name, size_bytes, content_type, storage_path, promoted_to, created_at
+                "#,
+                params![fmt_ts(&cutoff)],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        collect_job_artifacts(rows).await
+    }
+}
+
+async fn collect_job_artifacts(
+    mut rows: libsql::Rows,
+) -> Result<Vec<JobArtifactRecord>, DatabaseError> {
+    let mut artifacts = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    {
+        artifacts.push(JobArtifactRecord {
+            job_id: get_text(&row, 0).parse().unwrap_or_default(),
+            name: get_text(&row, 1),
+            size_bytes: get_i64(&row, 2),
+            content_type: get_text(&row, 3),
+            storage_path: get_text(&row, 4),
+            promoted_to: get_opt_text(&row, 5),
+            created_at: get_ts(&row, 6),
+        });
+    }
+    Ok(artifacts)
 }
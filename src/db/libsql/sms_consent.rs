@@ -0,0 +1,161 @@
+//! SmsConsentStore implementation for LibSqlBackend.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use libsql::params;
+
+use super::{LibSqlBackend, fmt_ts, get_opt_text, get_text, get_ts, opt_text_owned};
+use crate::db::{SetSmsConsentParams, SmsConsentRecord, SmsConsentStatus, SmsConsentStore};
+use crate::error::DatabaseError;
+
+const SMS_CONSENT_COLUMNS: &str =
+    "user_id, phone_number, client_id, status, source, created_at, updated_at";
+
+fn row_to_sms_consent(row: &libsql::Row) -> Result<SmsConsentRecord, DatabaseError> {
+    let status_raw = get_text(row, 3);
+    let status = SmsConsentStatus::from_db_value(&status_raw).ok_or_else(|| {
+        DatabaseError::Serialization(format!("invalid SMS consent status '{status_raw}'"))
+    })?;
+    Ok(SmsConsentRecord {
+        user_id: get_text(row, 0),
+        phone_number: get_text(row, 1),
+        client_id: get_opt_text(row, 2).and_then(|s| s.parse().ok()),
+        status,
+        source: get_text(row, 4),
+        created_at: get_ts(row, 5),
+        updated_at: get_ts(row, 6),
+    })
+}
+
+#[async_trait]
+impl SmsConsentStore for LibSqlBackend {
+    async fn get_sms_consent(
+        &self,
+        user_id: &str,
+        phone_number: &str,
+    ) -> Result<Option<SmsConsentRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {SMS_CONSENT_COLUMNS} FROM sms_consents \
+                     WHERE user_id = ?1 AND phone_number = ?2"
+                ),
+                params![user_id, phone_number],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        row_to_sms_consent(&row).map(Some)
+    }
+
+    async fn set_sms_consent(
+        &self,
+        user_id: &str,
+        params: &SetSmsConsentParams,
+    ) -> Result<SmsConsentRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        let now = fmt_ts(&Utc::now());
+        conn.execute(
+            "INSERT INTO sms_consents \
+             (user_id, phone_number, client_id, status, source, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6) \
+             ON CONFLICT (user_id, phone_number) DO UPDATE SET \
+               client_id = excluded.client_id, \
+               status = excluded.status, \
+               source = excluded.source, \
+               updated_at = excluded.updated_at",
+            params![
+                user_id,
+                params.phone_number.as_str(),
+                opt_text_owned(params.client_id.map(|id| id.to_string())),
+                params.status.as_str(),
+                params.source.as_str(),
+                now,
+            ],
+        )
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        self.get_sms_consent(user_id, &params.phone_number)
+            .await?
+            .ok_or_else(|| DatabaseError::NotFound {
+                entity: "sms_consent".to_string(),
+                id: params.phone_number.clone(),
+            })
+    }
+
+    async fn list_sms_consents(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<SmsConsentRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {SMS_CONSENT_COLUMNS} FROM sms_consents \
+                     WHERE user_id = ?1 ORDER BY updated_at DESC"
+                ),
+                params![user_id],
+            )
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?;
+        let mut records = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::Query(e.to_string()))?
+        {
+            records.push(row_to_sms_consent(&row)?);
+        }
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::{SetSmsConsentParams, SmsConsentStatus};
+
+    fn consent(status: SmsConsentStatus, source: &str) -> SetSmsConsentParams {
+        SetSmsConsentParams {
+            phone_number: "+15551234567".to_string(),
+            client_id: None,
+            status,
+            source: source.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn consent_upserts_keep_created_at() {
+        let (db, _tmp) = crate::testing::test_db().await;
+
+        assert!(
+            db.get_sms_consent("default", "+15551234567")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let first = db
+            .set_sms_consent("default", &consent(SmsConsentStatus::OptedIn, "START"))
+            .await
+            .unwrap();
+        assert_eq!(first.status, SmsConsentStatus::OptedIn);
+
+        let second = db
+            .set_sms_consent("default", &consent(SmsConsentStatus::OptedOut, "STOP"))
+            .await
+            .unwrap();
+        assert_eq!(second.status, SmsConsentStatus::OptedOut);
+        assert_eq!(second.source, "STOP");
+        assert_eq!(second.created_at, first.created_at);
+
+        let all = db.list_sms_consents("default").await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert!(db.list_sms_consents("other").await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,379 @@
+//! TemplateLibraryStore implementation for LibSqlBackend.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use libsql::params;
+use uuid::Uuid;
+
+use super::{LibSqlBackend, fmt_ts, get_i64, get_json, get_opt_text, get_text, get_ts, opt_text};
+use crate::db::{
+    DocumentTemplateUsageRecord, DocumentTemplateVersionRecord, RecordDocumentTemplateUseParams,
+    SetTemplateLibraryEntryParams, TemplateLibraryEntryRecord, TemplateLibraryStore,
+};
+use crate::error::DatabaseError;
+
+const LIBRARY_ENTRY_COLUMNS: &str = "template_id, user_id, practice_area, jurisdiction, \
+     description, promoted_from, updated_at";
+
+const TEMPLATE_VERSION_COLUMNS: &str = "id, template_id, version, name, body, variables_json, \
+     created_by, created_at";
+
+fn parse_uuid(raw: &str, field: &str) -> Result<Uuid, DatabaseError> {
+    raw.parse::<Uuid>()
+        .map_err(|e| DatabaseError::Serialization(format!("invalid {field} '{raw}': {e}")))
+}
+
+fn row_to_library_entry(row: &libsql::Row) -> Result<TemplateLibraryEntryRecord, DatabaseError> {
+    Ok(TemplateLibraryEntryRecord {
+        template_id: parse_uuid(&get_text(row, 0), "template id")?,
+        user_id: get_text(row, 1),
+        practice_area: get_opt_text(row, 2),
+        jurisdiction: get_opt_text(row, 3),
+        description: get_opt_text(row, 4),
+        promoted_from: get_opt_text(row, 5)
+            .map(|raw| parse_uuid(&raw, "promoted template id"))
+            .transpose()?,
+        updated_at: get_ts(row, 6),
+    })
+}
+
+fn row_to_template_version(
+    row: &libsql::Row,
+) -> Result<DocumentTemplateVersionRecord, DatabaseError> {
+    Ok(DocumentTemplateVersionRecord {
+        id: parse_uuid(&get_text(row, 0), "template version id")?,
+        template_id: parse_uuid(&get_text(row, 1), "template id")?,
+        version: get_i64(row, 2) as i32,
+        name: get_text(row, 3),
+        body: get_text(row, 4),
+        variables_json: get_json(row, 5),
+        created_by: get_text(row, 6),
+        created_at: get_ts(row, 7),
+    })
+}
+
+#[async_trait]
+impl TemplateLibraryStore for LibSqlBackend {
+    async fn set_template_library_entry(
+        &self,
+        user_id: &str,
+        template_id: Uuid,
+        input: &SetTemplateLibraryEntryParams,
+    ) -> Result<TemplateLibraryEntryRecord, DatabaseError> {
+        let conn = self.connect().await?;
+        let promoted_from = input.promoted_from.map(|id| id.to_string());
+        let mut rows = conn
+            .query(
+                &format!(
+                    "INSERT INTO document_template_library \
+                     (template_id, user_id, practice_area, jurisdiction, description, promoted_from) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6) \
+                     ON CONFLICT (template_id) DO UPDATE SET \
+                     practice_area = excluded.practice_area, \
+                     jurisdiction = excluded.jurisdiction, \
+                     description = excluded.description, \
+                     promoted_from = COALESCE(excluded.promoted_from, \
+                         document_template_library.promoted_from), \
+                     updated_at = datetime('now') \
+                     RETURNING {LIBRARY_ENTRY_COLUMNS}"
+                ),
+                params![
+                    template_id.to_string(),
+                    user_id,
+                    opt_text(input.practice_area.as_deref()),
+                    opt_text(input.jurisdiction.as_deref()),
+                    opt_text(input.description.as_deref()),
+                    opt_text(promoted_from.as_deref()),
+                ],
+            )
+            .await?;
+        let row = rows.next().await?.ok_or_else(|| {
+            DatabaseError::Query("template library upsert returned no row".to_string())
+        })?;
+        row_to_library_entry(&row)
+    }
+
+    async fn list_template_library_entries(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<TemplateLibraryEntryRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {LIBRARY_ENTRY_COLUMNS} FROM document_template_library \
+                     WHERE user_id = ?1"
+                ),
+                params![user_id],
+            )
+            .await?;
+        let mut entries = Vec::new();
+        while let Some(row) = rows.next().await? {
+            entries.push(row_to_library_entry(&row)?);
+        }
+        Ok(entries)
+    }
+
+    async fn record_document_template_version(
+        &self,
+        user_id: &str,
+        template_id: Uuid,
+        created_by: &str,
+    ) -> Result<Option<DocumentTemplateVersionRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        // IMMEDIATE so concurrent snapshots cannot pick the same version number.
+        conn.execute("BEGIN IMMEDIATE", ()).await?;
+
+        let op_result: Result<Option<DocumentTemplateVersionRecord>, DatabaseError> = async {
+            let mut rows = conn
+                .query(
+                    "SELECT name, body, variables_json FROM document_templates \
+                     WHERE user_id = ?1 AND id = ?2",
+                    params![user_id, template_id.to_string()],
+                )
+                .await?;
+            let Some(template) = rows.next().await? else {
+                return Ok(None);
+            };
+            let name = get_text(&template, 0);
+            let body = get_text(&template, 1);
+            let variables_json = get_json(&template, 2);
+
+            let mut rows = conn
+                .query(
+                    &format!(
+                        "SELECT {TEMPLATE_VERSION_COLUMNS} FROM document_template_versions \
+                         WHERE template_id = ?1 ORDER BY version DESC LIMIT 1"
+                    ),
+                    params![template_id.to_string()],
+                )
+                .await?;
+            let latest = match rows.next().await? {
+                Some(row) => Some(row_to_template_version(&row)?),
+                None => None,
+            };
+            if latest.as_ref().is_some_and(|latest| {
+                latest.name == name
+                    && latest.body == body
+                    && latest.variables_json == variables_json
+            }) {
+                return Ok(None);
+            }
+
+            let version = latest.map_or(1, |latest| latest.version + 1);
+            let mut rows = conn
+                .query(
+                    &format!(
+                        "INSERT INTO document_template_versions \
+                         (id, template_id, user_id, version, name, body, variables_json, \
+                          created_by, created_at) \
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9) \
+                         RETURNING {TEMPLATE_VERSION_COLUMNS}"
+                    ),
+                    params![
+                        Uuid::new_v4().to_string(),
+                        template_id.to_string(),
+                        user_id,
+                        i64::from(version),
+                        name,
+                        body,
+                        variables_json.to_string(),
+                        created_by,
+                        fmt_ts(&Utc::now()),
+                    ],
+                )
+                .await?;
+            let row = rows.next().await?.ok_or_else(|| {
+                DatabaseError::Query("template version insert returned no row".to_string())
+            })?;
+            row_to_template_version(&row).map(Some)
+        }
+        .await;
+
+        match op_result {
+            Ok(version) => {
+                conn.execute("COMMIT", ()).await?;
+                Ok(version)
+            }
+            Err(err) => {
+                let _ = conn.execute("ROLLBACK", ()).await;
+                Err(err)
+            }
+        }
+    }
+
+    async fn list_document_template_versions(
+        &self,
+        user_id: &str,
+        template_id: Uuid,
+    ) -> Result<Vec<DocumentTemplateVersionRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {TEMPLATE_VERSION_COLUMNS} FROM document_template_versions \
+                     WHERE user_id = ?1 AND template_id = ?2 ORDER BY version DESC"
+                ),
+                params![user_id, template_id.to_string()],
+            )
+            .await?;
+        let mut versions = Vec::new();
+        while let Some(row) = rows.next().await? {
+            versions.push(row_to_template_version(&row)?);
+        }
+        Ok(versions)
+    }
+
+    async fn record_document_template_use(
+        &self,
+        user_id: &str,
+        input: &RecordDocumentTemplateUseParams,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.connect().await?;
+        conn.execute(
+            "INSERT INTO document_template_uses \
+             (id, user_id, template_id, matter_id, document_path, used_by, used_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                Uuid::new_v4().to_string(),
+                user_id,
+                input.template_id.to_string(),
+                input.matter_id.as_str(),
+                input.document_path.as_str(),
+                input.used_by.as_str(),
+                fmt_ts(&Utc::now()),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn document_template_usage(
+        &self,
+        user_id: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<DocumentTemplateUsageRecord>, DatabaseError> {
+        let conn = self.connect().await?;
+        let since = since.map(|since| fmt_ts(&since));
+        let mut rows = conn
+            .query(
+                "SELECT template_id, COUNT(*) AS uses, COUNT(DISTINCT matter_id), \
+                 MAX(used_at) AS last_used_at \
+                 FROM document_template_uses \
+                 WHERE user_id = ?1 AND (?2 IS NULL OR used_at >= ?2) \
+                 GROUP BY template_id \
+                 ORDER BY uses DESC, last_used_at DESC",
+                params![user_id, opt_text(since.as_deref())],
+            )
+            .await?;
+        let mut usage = Vec::new();
+        while let Some(row) = rows.next().await? {
+            usage.push(DocumentTemplateUsageRecord {
+                template_id: parse_uuid(&get_text(&row, 0), "template id")?,
+                uses: get_i64(&row, 1),
+                matters: get_i64(&row, 2),
+                last_used_at: get_ts(&row, 3),
+            });
+        }
+        Ok(usage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::db::{
+        RecordDocumentTemplateUseParams, UpdateDocumentTemplateParams, UpsertDocumentTemplateParams,
+    };
+
+    #[tokio::test]
+    async fn template_versions_skip_unchanged_snapshots_and_delete_with_template() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let template = db
+            .upsert_document_template(
+                "default",
+                &UpsertDocumentTemplateParams {
+                    matter_id: None,
+                    name: "engagement.md".to_string(),
+                    body: "Dear {{ client.name }}".to_string(),
+                    variables_json: serde_json::json!([]),
+                },
+            )
+            .await
+            .unwrap();
+
+        let first = db
+            .record_document_template_version("default", template.id, "default")
+            .await
+            .unwrap()
+            .expect("first snapshot");
+        assert_eq!(first.version, 1);
+        assert!(
+            db.record_document_template_version("default", template.id, "default")
+                .await
+                .unwrap()
+                .is_none(),
+            "unchanged content records no version"
+        );
+        db.update_document_template(
+            "default",
+            template.id,
+            &UpdateDocumentTemplateParams {
+                name: None,
+                body: Some("Dear {{ client.name }},".to_string()),
+                variables_json: None,
+            },
+        )
+        .await
+        .unwrap();
+        let second = db
+            .record_document_template_version("default", template.id, "default")
+            .await
+            .unwrap()
+            .expect("second snapshot");
+        assert_eq!(second.version, 2);
+
+        for matter_id in ["acme", "acme", "globex"] {
+            db.record_document_template_use(
+                "default",
+                &RecordDocumentTemplateUseParams {
+                    template_id: template.id,
+                    matter_id: matter_id.to_string(),
+                    document_path: format!("matters/{matter_id}/drafts/engagement.md"),
+                    used_by: "default".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        }
+        let usage = db.document_template_usage("default", None).await.unwrap();
+        assert_eq!(usage.len(), 1);
+        assert_eq!((usage[0].uses, usage[0].matters), (3, 2));
+        let future = Utc::now() + Duration::hours(1);
+        assert!(
+            db.document_template_usage("default", Some(future))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        assert!(
+            db.delete_document_template("default", template.id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            db.list_document_template_versions("default", template.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            db.document_template_usage("default", None)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
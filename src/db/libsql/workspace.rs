@@ -8,13 +8,14 @@ use libsql::params;
 use uuid::Uuid;
 
 use super::{
-    LibSqlBackend, fmt_ts, get_i64, get_opt_text, get_opt_ts, get_text, get_ts,
+    LibSqlBackend, fmt_ts, get_i64, get_json, get_opt_text, get_opt_ts, get_text, get_ts,
     row_to_memory_document,
 };
 use crate::db::WorkspaceStore;
 use crate::error::WorkspaceError;
 use crate::workspace::{
-    MemoryChunk, MemoryDocument, RankedResult, SearchConfig, SearchResult, WorkspaceEntry,
+    CitedChunk, DocumentRevision, MemoryChunk, MemoryDocument, RankedResult, SearchConfig,
+    SearchResult, TrashedDocument, WorkspaceEntry, WorkspaceStorageUsage, history,
     reciprocal_rank_fusion,
 };
 
@@ -22,6 +23,77 @@ use chrono::Utc;
 
 static VECTOR_FALLBACK_WARN_ONCE: Once = Once::new();
 
+const TRASH_COLUMNS: &str = "id, user_id, agent_id, path, content, metadata, deleted_by, \
+     document_created_at, document_updated_at, deleted_at";
+
+fn row_to_trashed_document(row: &libsql::Row) -> TrashedDocument {
+    TrashedDocument {
+        id: get_text(row, 0).parse().unwrap_or_default(),
+        user_id: get_text(row, 1),
+        agent_id: get_opt_text(row, 2).and_then(|s| s.parse().ok()),
+        path: get_text(row, 3),
+        content: get_text(row, 4),
+        metadata: get_json(row, 5),
+        deleted_by: get_text(row, 6),
+        document_created_at: get_ts(row, 7),
+        document_updated_at: get_ts(row, 8),
+        deleted_at: get_ts(row, 9),
+    }
+}
+
+fn trash_err(e: impl std::fmt::Display) -> WorkspaceError {
+    WorkspaceError::SearchFailed {
+        reason: format!("Trash operation failed: {e}"),
+    }
+}
+
+const REVISION_COLUMNS: &str = "id, document_id, revision, content, content_hash, diff, created_at";
+
+fn row_to_document_revision(row: &libsql::Row) -> DocumentRevision {
+    DocumentRevision {
+        id: get_text(row, 0).parse().unwrap_or_default(),
+        document_id: get_text(row, 1).parse().unwrap_or_default(),
+        revision: get_i64(row, 2),
+        content: get_text(row, 3),
+        content_hash: get_text(row, 4),
+        diff: get_opt_text(row, 5),
+        created_at: get_ts(row, 6),
+    }
+}
+
+fn revision_err(e: impl std::fmt::Display) -> WorkspaceError {
+    WorkspaceError::SearchFailed {
+        reason: format!("Revision query failed: {e}"),
+    }
+}
+
+impl LibSqlBackend {
+    fn open_revision(
+        &self,
+        mut revision: DocumentRevision,
+    ) -> Result<DocumentRevision, WorkspaceError> {
+        revision.content = self.open_ws(revision.content)?;
+        revision.diff = revision.diff.map(|diff| self.open_ws(diff)).transpose()?;
+        Ok(revision)
+    }
+}
+
+async fn query_trashed_documents(
+    backend: &LibSqlBackend,
+    conn: &libsql::Connection,
+    sql: &str,
+    params: impl libsql::params::IntoParams,
+) -> Result<Vec<TrashedDocument>, WorkspaceError> {
+    let mut rows = conn.query(sql, params).await.map_err(trash_err)?;
+    let mut docs = Vec::new();
+    while let Some(row) = rows.next().await.map_err(trash_err)? {
+        let mut doc = row_to_trashed_document(&row);
+        doc.content = backend.open_ws(doc.content)?;
+        docs.push(doc);
+    }
+    Ok(docs)
+}
+
 #[async_trait]
 impl WorkspaceStore for LibSqlBackend {
     async fn get_document_by_path(
@@ -58,7 +130,11 @@ impl WorkspaceStore for LibSqlBackend {
             .map_err(|e| WorkspaceError::SearchFailed {
                 reason: format!("Query failed: {}", e),
             })? {
-            Some(row) => Ok(row_to_memory_document(&row)),
+            Some(row) => {
+                let mut doc = row_to_memory_document(&row);
+                doc.content = self.open_ws(doc.content)?;
+                Ok(doc)
+            }
             None => Err(WorkspaceError::DocumentNotFound {
                 doc_type: path.to_string(),
                 user_id: user_id.to_string(),
@@ -93,7 +169,11 @@ impl WorkspaceStore for LibSqlBackend {
             .map_err(|e| WorkspaceError::SearchFailed {
                 reason: format!("Query failed: {}", e),
             })? {
-            Some(row) => Ok(row_to_memory_document(&row)),
+            Some(row) => {
+                let mut doc = row_to_memory_document(&row);
+                doc.content = self.open_ws(doc.content)?;
+                Ok(doc)
+            }
             None => Err(WorkspaceError::DocumentNotFound {
                 doc_type: "unknown".to_string(),
                 user_id: "unknown".to_string(),
@@ -139,25 +219,165 @@ impl WorkspaceStore for LibSqlBackend {
         self.get_document_by_path(user_id, agent_id, path).await
     }
 
-    async fn update_document(&self, id: Uuid, content: &str) -> Result<(), WorkspaceError> {
+    async fn update_document(
+        &self,
+        id: Uuid,
+        content: &str,
+        if_match: Option<&str>,
+    ) -> Result<(), WorkspaceError> {
+        let update_err = |e: libsql::Error| WorkspaceError::SearchFailed {
+            reason: format!("Update failed: {}", e),
+        };
         let conn = self
             .connect()
             .await
             .map_err(|e| WorkspaceError::SearchFailed {
                 reason: e.to_string(),
             })?;
+        let id_str = id.to_string();
         let now = fmt_ts(&Utc::now());
-        conn.execute(
-            "UPDATE memory_documents SET content = ?2, updated_at = ?3 WHERE id = ?1",
-            params![id.to_string(), content, now],
-        )
-        .await
-        .map_err(|e| WorkspaceError::SearchFailed {
-            reason: format!("Update failed: {}", e),
-        })?;
+        // IMMEDIATE so concurrent writers cannot claim the same revision number
+        // or slip in between the If-Match check and the update.
+        conn.execute("BEGIN IMMEDIATE", ())
+            .await
+            .map_err(update_err)?;
+        let op_result: Result<(), WorkspaceError> = async {
+            let mut rows = conn
+                .query(
+                    "SELECT content FROM memory_documents WHERE id = ?1",
+                    params![id_str.as_str()],
+                )
+                .await
+                .map_err(update_err)?;
+            let previous = rows
+                .next()
+                .await
+                .map_err(update_err)?
+                .map(|row| self.open_ws(get_text(&row, 0)))
+                .transpose()?;
+            drop(rows);
+            if let Some(if_match) = if_match {
+                match previous.as_deref() {
+                    Some(previous) if history::etag_matches(if_match, previous) => {}
+                    previous => {
+                        return Err(WorkspaceError::WriteConflict {
+                            current_etag: previous.map(history::content_hash),
+                        });
+                    }
+                }
+            }
+            let stored = self.seal_ws(content)?;
+            conn.execute(
+                "UPDATE memory_documents SET content = ?2, updated_at = ?3 WHERE id = ?1",
+                params![id_str.as_str(), stored.as_ref(), now.as_str()],
+            )
+            .await
+            .map_err(update_err)?;
+
+            let Some(previous) = previous.filter(|previous| previous != content) else {
+                return Ok(());
+            };
+            let mut rows = conn
+                .query(
+                    "SELECT COALESCE(MAX(revision), 0) FROM memory_document_revisions \
+                     WHERE document_id = ?1",
+                    params![id_str.as_str()],
+                )
+                .await
+                .map_err(update_err)?;
+            let latest = match rows.next().await.map_err(update_err)? {
+                Some(row) => get_i64(&row, 0),
+                None => 0,
+            };
+            drop(rows);
+            // Documents written before history existed get a baseline revision.
+            let mut revisions = Vec::new();
+            if latest == 0 && !previous.is_empty() {
+                revisions.push((previous.clone(), None));
+            }
+            revisions.push((
+                content.to_string(),
+                history::stored_revision_diff(&previous, content),
+            ));
+            for (offset, (revision_content, diff)) in revisions.into_iter().enumerate() {
+                let content_hash = history::content_hash(&revision_content);
+                let revision_content = self.seal_ws(&revision_content)?.into_owned();
+                let diff = diff
+                    .map(|diff| self.seal_ws(&diff).map(|d| d.into_owned()))
+                    .transpose()?;
+                conn.execute(
+                    "INSERT INTO memory_document_revisions \
+                     (id, document_id, revision, content, content_hash, diff, created_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    params![
+                        Uuid::new_v4().to_string(),
+                        id_str.as_str(),
+                        latest + offset as i64 + 1,
+                        revision_content,
+                        content_hash,
+                        diff,
+                        now.as_str(),
+                    ],
+                )
+                .await
+                .map_err(update_err)?;
+            }
+            Ok(())
+        }
+        .await;
+        if let Err(err) = op_result {
+            let _ = conn.execute("ROLLBACK", ()).await;
+            return Err(err);
+        }
+        conn.execute("COMMIT", ()).await.map_err(update_err)?;
         Ok(())
     }
 
+    async fn list_document_revisions(
+        &self,
+        document_id: Uuid,
+    ) -> Result<Vec<DocumentRevision>, WorkspaceError> {
+        let conn = self.connect().await.map_err(revision_err)?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {REVISION_COLUMNS} FROM memory_document_revisions \
+                     WHERE document_id = ?1 ORDER BY revision DESC"
+                ),
+                params![document_id.to_string()],
+            )
+            .await
+            .map_err(revision_err)?;
+        let mut revisions = Vec::new();
+        while let Some(row) = rows.next().await.map_err(revision_err)? {
+            revisions.push(self.open_revision(row_to_document_revision(&row))?);
+        }
+        Ok(revisions)
+    }
+
+    async fn get_document_revision(
+        &self,
+        document_id: Uuid,
+        revision: i64,
+    ) -> Result<Option<DocumentRevision>, WorkspaceError> {
+        let conn = self.connect().await.map_err(revision_err)?;
+        let mut rows = conn
+            .query(
+                &format!(
+                    "SELECT {REVISION_COLUMNS} FROM memory_document_revisions \
+                     WHERE document_id = ?1 AND revision = ?2"
+                ),
+                params![document_id.to_string(), revision],
+            )
+            .await
+            .map_err(revision_err)?;
+        rows.next()
+            .await
+            .map_err(revision_err)?
+            .map(|row| self.open_revision(row_to_document_revision(&row)))
+            .transpose()
+    }
+
     async fn delete_document_by_path(
         &self,
         user_id: &str,
@@ -173,6 +393,14 @@ impl WorkspaceStore for LibSqlBackend {
             .map_err(|e| WorkspaceError::SearchFailed {
                 reason: e.to_string(),
             })?;
+        conn.execute(
+            "DELETE FROM memory_document_revisions WHERE document_id = ?1",
+            params![doc.id.to_string()],
+        )
+        .await
+        .map_err(|e| WorkspaceError::SearchFailed {
+            reason: format!("Delete failed: {}", e),
+        })?;
         let agent_id_str = agent_id.map(|id| id.to_string());
         conn.execute(
             "DELETE FROM memory_documents WHERE user_id = ?1 AND agent_id IS ?2 AND path = ?3",
@@ -185,6 +413,151 @@ impl WorkspaceStore for LibSqlBackend {
         Ok(())
     }
 
+    async fn trash_document_by_path(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        path: &str,
+        deleted_by: &str,
+    ) -> Result<TrashedDocument, WorkspaceError> {
+        let doc = self.get_document_by_path(user_id, agent_id, path).await?;
+        let stored_content = self.seal_ws(&doc.content)?;
+        let conn = self.connect().await.map_err(trash_err)?;
+        let trash_id = Uuid::new_v4();
+        let doc_id = doc.id.to_string();
+        conn.execute("BEGIN", ()).await.map_err(trash_err)?;
+        let op_result: Result<(), libsql::Error> = async {
+            conn.execute(
+                "INSERT INTO memory_trash \
+                 (id, user_id, agent_id, path, content, metadata, deleted_by, \
+                  document_created_at, document_updated_at, deleted_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    trash_id.to_string(),
+                    user_id,
+                    agent_id.map(|id| id.to_string()),
+                    path,
+                    stored_content.as_ref(),
+                    doc.metadata.to_string(),
+                    deleted_by,
+                    fmt_ts(&doc.created_at),
+                    fmt_ts(&doc.updated_at),
+                    fmt_ts(&Utc::now()),
+                ],
+            )
+            .await?;
+            conn.execute(
+                "DELETE FROM memory_chunks WHERE document_id = ?1",
+                params![doc_id.as_str()],
+            )
+            .await?;
+            conn.execute(
+                "DELETE FROM memory_document_revisions WHERE document_id = ?1",
+                params![doc_id.as_str()],
+            )
+            .await?;
+            conn.execute(
+                "DELETE FROM memory_documents WHERE id = ?1",
+                params![doc_id.as_str()],
+            )
+            .await?;
+            Ok(())
+        }
+        .await;
+        if let Err(err) = op_result {
+            let _ = conn.execute("ROLLBACK", ()).await;
+            return Err(trash_err(err));
+        }
+        conn.execute("COMMIT", ()).await.map_err(trash_err)?;
+        self.get_trashed_document(user_id, agent_id, trash_id)
+            .await?
+            .ok_or_else(|| trash_err("trash entry missing after insert"))
+    }
+
+    async fn list_trashed_documents(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+    ) -> Result<Vec<TrashedDocument>, WorkspaceError> {
+        let conn = self.connect().await.map_err(trash_err)?;
+        query_trashed_documents(
+            self,
+            &conn,
+            &format!(
+                "SELECT {TRASH_COLUMNS} FROM memory_trash \
+                 WHERE user_id = ?1 AND agent_id IS ?2 ORDER BY deleted_at DESC, path"
+            ),
+            params![user_id, agent_id.map(|id| id.to_string())],
+        )
+        .await
+    }
+
+    async fn get_trashed_document(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        id: Uuid,
+    ) -> Result<Option<TrashedDocument>, WorkspaceError> {
+        let conn = self.connect().await.map_err(trash_err)?;
+        Ok(query_trashed_documents(
+            self,
+            &conn,
+            &format!(
+                "SELECT {TRASH_COLUMNS} FROM memory_trash \
+                 WHERE user_id = ?1 AND agent_id IS ?2 AND id = ?3"
+            ),
+            params![user_id, agent_id.map(|id| id.to_string()), id.to_string()],
+        )
+        .await?
+        .pop())
+    }
+
+    async fn delete_trashed_document(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        id: Uuid,
+    ) -> Result<bool, WorkspaceError> {
+        let conn = self.connect().await.map_err(trash_err)?;
+        let deleted = conn
+            .execute(
+                "DELETE FROM memory_trash WHERE user_id = ?1 AND agent_id IS ?2 AND id = ?3",
+                params![user_id, agent_id.map(|id| id.to_string()), id.to_string()],
+            )
+            .await
+            .map_err(trash_err)?;
+        Ok(deleted > 0)
+    }
+
+    async fn purge_trashed_documents(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        cutoff: chrono::DateTime<Utc>,
+    ) -> Result<Vec<TrashedDocument>, WorkspaceError> {
+        let conn = self.connect().await.map_err(trash_err)?;
+        let agent_id = agent_id.map(|id| id.to_string());
+        let expired = query_trashed_documents(
+            self,
+            &conn,
+            &format!(
+                "SELECT {TRASH_COLUMNS} FROM memory_trash \
+                 WHERE user_id = ?1 AND agent_id IS ?2 AND deleted_at < ?3 ORDER BY deleted_at"
+            ),
+            params![user_id, agent_id.as_deref(), fmt_ts(&cutoff)],
+        )
+        .await?;
+        for doc in &expired {
+            conn.execute(
+                "DELETE FROM memory_trash WHERE id = ?1",
+                params![doc.id.to_string()],
+            )
+            .await
+            .map_err(trash_err)?;
+        }
+        Ok(expired)
+    }
+
     async fn list_directory(
         &self,
         user_id: &str,
@@ -213,13 +586,20 @@ impl WorkspaceStore for LibSqlBackend {
         let mut rows = conn
             .query(
                 r#"
-                SELECT path, updated_at, substr(content, 1, 200) as content_preview
+                SELECT path, updated_at,
+                       CASE WHEN ?4 THEN content ELSE substr(content, 1, 200) END
+                           AS content_preview
                 FROM memory_documents
                 WHERE user_id = ?1 AND agent_id IS ?2
                   AND (?3 = '%' OR path LIKE ?3)
                 ORDER BY path
                 "#,
-                params![user_id, agent_id_str.as_deref(), pattern],
+                params![
+                    user_id,
+                    agent_id_str.as_deref(),
+                    pattern,
+                    self.content_encryption_enabled()
+                ],
             )
             .await
             .map_err(|e| WorkspaceError::SearchFailed {
@@ -237,7 +617,11 @@ impl WorkspaceStore for LibSqlBackend {
         {
             let full_path = get_text(&row, 0);
             let updated_at = get_opt_ts(&row, 1);
-            let content_preview = get_opt_text(&row, 2);
+            // Sealed content is opened in full and cut to the preview length here.
+            let content_preview = get_opt_text(&row, 2)
+                .map(|stored| self.open_ws(stored))
+                .transpose()?
+                .map(|content| content.chars().take(200).collect::<String>());
 
             let relative = if dir.is_empty() {
                 &full_path
@@ -361,7 +745,9 @@ impl WorkspaceStore for LibSqlBackend {
                 reason: format!("Query failed: {}", e),
             })?
         {
-            docs.push(row_to_memory_document(&row));
+            let mut doc = row_to_memory_document(&row);
+            doc.content = self.open_ws(doc.content)?;
+            docs.push(doc);
         }
         Ok(docs)
     }
@@ -398,6 +784,7 @@ impl WorkspaceStore for LibSqlBackend {
                 reason: e.to_string(),
             })?;
         let id = Uuid::new_v4();
+        let stored = self.seal_ws(content)?;
         let embedding_blob = embedding.map(|e| {
             let bytes: Vec<u8> = e.iter().flat_map(|f| f.to_le_bytes()).collect();
             bytes
@@ -412,7 +799,7 @@ impl WorkspaceStore for LibSqlBackend {
                 id.to_string(),
                 document_id.to_string(),
                 chunk_index as i64,
-                content,
+                stored.as_ref(),
                 embedding_blob.map(libsql::Value::Blob),
             ],
         )
@@ -489,7 +876,7 @@ impl WorkspaceStore for LibSqlBackend {
                 id: get_text(&row, 0).parse().unwrap_or_default(),
                 document_id: get_text(&row, 1).parse().unwrap_or_default(),
                 chunk_index: get_i64(&row, 2) as i32,
-                content: get_text(&row, 3),
+                content: self.open_ws(get_text(&row, 3))?,
                 embedding: None,
                 created_at: get_ts(&row, 4),
             });
@@ -497,6 +884,52 @@ impl WorkspaceStore for LibSqlBackend {
         Ok(chunks)
     }
 
+    async fn get_chunk(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        chunk_id: Uuid,
+    ) -> Result<Option<CitedChunk>, WorkspaceError> {
+        let conn = self
+            .connect()
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: e.to_string(),
+            })?;
+        let agent_id_str = agent_id.map(|id| id.to_string());
+        let mut rows = conn
+            .query(
+                r#"
+                SELECT c.id, c.document_id, d.path, c.chunk_index, c.content
+                FROM memory_chunks c
+                JOIN memory_documents d ON d.id = c.document_id
+                WHERE c.id = ?1 AND d.user_id = ?2 AND d.agent_id IS ?3
+                "#,
+                params![chunk_id.to_string(), user_id, agent_id_str.as_deref()],
+            )
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Query failed: {}", e),
+            })?;
+
+        let row = rows
+            .next()
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Query failed: {}", e),
+            })?;
+        row.map(|row| {
+            Ok(CitedChunk {
+                chunk_id: get_text(&row, 0).parse().unwrap_or_default(),
+                document_id: get_text(&row, 1).parse().unwrap_or_default(),
+                path: get_text(&row, 2),
+                chunk_index: get_i64(&row, 3) as i32,
+                content: self.open_ws(get_text(&row, 4))?,
+            })
+        })
+        .transpose()
+    }
+
     async fn hybrid_search(
         &self,
         user_id: &str,
@@ -513,21 +946,29 @@ impl WorkspaceStore for LibSqlBackend {
             })?;
         let agent_id_str = agent_id.map(|id| id.to_string());
         let pre_limit = config.pre_fusion_limit as i64;
+        let path_prefix = config.path_prefix_filter();
 
         let fts_results = if config.use_fts {
             let mut rows = conn
                 .query(
                     r#"
-                    SELECT c.id, c.document_id, c.content
+                    SELECT c.id, c.document_id, d.path, c.chunk_index, c.content
                     FROM memory_chunks_fts fts
                     JOIN memory_chunks c ON c._rowid = fts.rowid
                     JOIN memory_documents d ON d.id = c.document_id
                     WHERE d.user_id = ?1 AND d.agent_id IS ?2
                       AND memory_chunks_fts MATCH ?3
+                      AND (?5 IS NULL OR substr(d.path, 1, length(?5)) = ?5)
                     ORDER BY rank
                     LIMIT ?4
                     "#,
-                    params![user_id, agent_id_str.as_deref(), query, pre_limit],
+                    params![
+                        user_id,
+                        agent_id_str.as_deref(),
+                        query,
+                        pre_limit,
+                        path_prefix.as_deref()
+                    ],
                 )
                 .await
                 .map_err(|e| WorkspaceError::SearchFailed {
@@ -545,7 +986,9 @@ impl WorkspaceStore for LibSqlBackend {
                 results.push(RankedResult {
                     chunk_id: get_text(&row, 0).parse().unwrap_or_default(),
                     document_id: get_text(&row, 1).parse().unwrap_or_default(),
-                    content: get_text(&row, 2),
+                    path: get_text(&row, 2),
+                    chunk_index: get_i64(&row, 3) as i32,
+                    content: self.open_ws(get_text(&row, 4))?,
                     rank: results.len() as u32 + 1,
                 });
             }
@@ -563,18 +1006,49 @@ impl WorkspaceStore for LibSqlBackend {
                     .join(",")
             );
 
-            let vector_rows = conn
-                .query(
-                    r#"
-                    SELECT c.id, c.document_id, c.content
-                    FROM vector_top_k('idx_memory_chunks_embedding', vector(?1), ?2) AS top_k
-                    JOIN memory_chunks c ON c._rowid = top_k.id
-                    JOIN memory_documents d ON d.id = c.document_id
-                    WHERE d.user_id = ?3 AND d.agent_id IS ?4
-                    "#,
-                    params![vector_json, pre_limit, user_id, agent_id_str.as_deref()],
-                )
-                .await;
+            // `vector_top_k` picks its neighbours across the whole index
+            // before any WHERE clause applies, so under a path prefix the
+            // nearest chunks usually belong to other matters and the scoped
+            // result comes back empty. Scoped searches rank the prefix's
+            // chunks by exact distance instead; one matter is small enough
+            // to scan.
+            let vector_rows = match path_prefix.as_deref() {
+                Some(prefix) => {
+                    conn.query(
+                        r#"
+                        SELECT c.id, c.document_id, d.path, c.chunk_index, c.content
+                        FROM memory_chunks c
+                        JOIN memory_documents d ON d.id = c.document_id
+                        WHERE d.user_id = ?3 AND d.agent_id IS ?4
+                          AND substr(d.path, 1, length(?5)) = ?5
+                          AND c.embedding IS NOT NULL
+                        ORDER BY vector_distance_cos(c.embedding, vector(?1))
+                        LIMIT ?2
+                        "#,
+                        params![
+                            vector_json,
+                            pre_limit,
+                            user_id,
+                            agent_id_str.as_deref(),
+                            prefix
+                        ],
+                    )
+                    .await
+                }
+                None => {
+                    conn.query(
+                        r#"
+                        SELECT c.id, c.document_id, d.path, c.chunk_index, c.content
+                        FROM vector_top_k('idx_memory_chunks_embedding', vector(?1), ?2) AS top_k
+                        JOIN memory_chunks c ON c._rowid = top_k.id
+                        JOIN memory_documents d ON d.id = c.document_id
+                        WHERE d.user_id = ?3 AND d.agent_id IS ?4
+                        "#,
+                        params![vector_json, pre_limit, user_id, agent_id_str.as_deref()],
+                    )
+                    .await
+                }
+            };
 
             let mut rows = match vector_rows {
                 Ok(rows) => rows,
@@ -610,7 +1084,9 @@ impl WorkspaceStore for LibSqlBackend {
                 results.push(RankedResult {
                     chunk_id: get_text(&row, 0).parse().unwrap_or_default(),
                     document_id: get_text(&row, 1).parse().unwrap_or_default(),
-                    content: get_text(&row, 2),
+                    path: get_text(&row, 2),
+                    chunk_index: get_i64(&row, 3) as i32,
+                    content: self.open_ws(get_text(&row, 4))?,
                     rank: results.len() as u32 + 1,
                 });
             }
@@ -627,6 +1103,91 @@ impl WorkspaceStore for LibSqlBackend {
 
         Ok(reciprocal_rank_fusion(fts_results, vector_results, config))
     }
+
+    async fn storage_usage(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        path_prefix: Option<&str>,
+    ) -> Result<WorkspaceStorageUsage, WorkspaceError> {
+        let conn = self
+            .connect()
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: e.to_string(),
+            })?;
+        let agent_id_str = agent_id.map(|id| id.to_string());
+        let envelope_prefix = crate::legal::workspace_crypto::encrypted_payload_prefix();
+
+        let doc_row = conn
+            .query(
+                r#"
+                SELECT
+                    COUNT(*),
+                    COALESCE(SUM(LENGTH(CAST(content AS BLOB))), 0),
+                    COALESCE(SUM(CASE WHEN substr(content, 1, length(?4)) = ?4 THEN 1 ELSE 0 END), 0),
+                    COALESCE(SUM(CASE WHEN substr(content, 1, length(?4)) = ?4
+                        THEN LENGTH(CAST(content AS BLOB)) ELSE 0 END), 0)
+                FROM memory_documents
+                WHERE user_id = ?1 AND agent_id IS ?2
+                  AND (?3 IS NULL OR substr(path, 1, length(?3)) = ?3)
+                "#,
+                params![
+                    user_id,
+                    agent_id_str.as_deref(),
+                    path_prefix,
+                    envelope_prefix.as_str()
+                ],
+            )
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Storage usage query failed: {}", e),
+            })?
+            .next()
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Storage usage query failed: {}", e),
+            })?;
+
+        let chunk_row = conn
+            .query(
+                r#"
+                SELECT
+                    COUNT(c.id),
+                    COALESCE(SUM(LENGTH(CAST(c.content AS BLOB))), 0),
+                    COALESCE(SUM(CASE WHEN c.embedding IS NOT NULL THEN 1 ELSE 0 END), 0)
+                FROM memory_chunks c
+                JOIN memory_documents d ON d.id = c.document_id
+                WHERE d.user_id = ?1 AND d.agent_id IS ?2
+                  AND (?3 IS NULL OR substr(d.path, 1, length(?3)) = ?3)
+                "#,
+                params![user_id, agent_id_str.as_deref(), path_prefix],
+            )
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Storage usage query failed: {}", e),
+            })?
+            .next()
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Storage usage query failed: {}", e),
+            })?;
+
+        let count = |row: &Option<libsql::Row>, idx: i32| -> u64 {
+            row.as_ref()
+                .map(|row| get_i64(row, idx).max(0) as u64)
+                .unwrap_or(0)
+        };
+        Ok(WorkspaceStorageUsage {
+            document_count: count(&doc_row, 0),
+            document_bytes: count(&doc_row, 1),
+            binary_count: count(&doc_row, 2),
+            binary_bytes: count(&doc_row, 3),
+            chunk_count: count(&chunk_row, 0),
+            chunk_bytes: count(&chunk_row, 1),
+            embedded_chunk_count: count(&chunk_row, 2),
+        })
+    }
 }
 
 fn is_vector_runtime_unavailable_error(reason: &str) -> bool {
@@ -704,6 +1265,7 @@ mod tests {
             limit: 10,
             rrf_k: 60,
             min_score: 0.0,
+            path_prefix: None,
         };
 
         let embedding = vec![0.1; 1536];
@@ -716,4 +1278,239 @@ mod tests {
             "expected at least one FTS result when vector path is unavailable"
         );
     }
+
+    #[tokio::test]
+    async fn hybrid_search_path_prefix_excludes_other_matters() {
+        let tmp = tempdir().expect("tempdir");
+        let db_path = tmp.path().join("workspace.db");
+        let backend = LibSqlBackend::new_local(&db_path)
+            .await
+            .expect("local libsql backend");
+        backend.run_migrations().await.expect("run migrations");
+
+        let user_id = "u1";
+        for (path, content) in [
+            ("matters/acme/notes.md", "Alpha deposition outline"),
+            ("matters/acme-2/notes.md", "Alpha settlement memo"),
+            ("MEMORY.md", "Alpha general preference"),
+        ] {
+            let document = backend
+                .get_or_create_document_by_path(user_id, None, path)
+                .await
+                .expect("create doc");
+            backend
+                .insert_chunk(document.id, 0, content, None)
+                .await
+                .expect("insert chunk");
+        }
+
+        let unscoped = backend
+            .hybrid_search(user_id, None, "Alpha", None, &SearchConfig::default())
+            .await
+            .expect("unscoped search");
+        assert_eq!(unscoped.len(), 3);
+
+        let config = SearchConfig::default().with_path_prefix("matters/acme");
+        let scoped = backend
+            .hybrid_search(user_id, None, "Alpha", None, &config)
+            .await
+            .expect("scoped search");
+        assert_eq!(scoped.len(), 1);
+        assert_eq!(scoped[0].content, "Alpha deposition outline");
+    }
+
+    #[tokio::test]
+    async fn trashed_documents_drop_chunks_and_purge_by_cutoff() {
+        let tmp = tempdir().expect("tempdir");
+        let db_path = tmp.path().join("workspace.db");
+        let backend = LibSqlBackend::new_local(&db_path)
+            .await
+            .expect("local libsql backend");
+        backend.run_migrations().await.expect("run migrations");
+
+        let user_id = "u1";
+        let path = "matters/acme/notes.md";
+        let document = backend
+            .get_or_create_document_by_path(user_id, None, path)
+            .await
+            .expect("create doc");
+        backend
+            .update_document(document.id, "Alpha deposition outline", None)
+            .await
+            .expect("write doc");
+        backend
+            .insert_chunk(document.id, 0, "Alpha deposition outline", None)
+            .await
+            .expect("insert chunk");
+
+        let trashed = backend
+            .trash_document_by_path(user_id, None, path, "associate")
+            .await
+            .expect("trash doc");
+        assert_eq!(trashed.content, "Alpha deposition outline");
+        assert_eq!(trashed.deleted_by, "associate");
+        assert!(
+            backend
+                .get_document_by_path(user_id, None, path)
+                .await
+                .is_err()
+        );
+        let conn = backend.connect().await.expect("connect");
+        let mut rows = conn
+            .query("SELECT COUNT(*) FROM memory_chunks", ())
+            .await
+            .expect("count chunks");
+        let row = rows.next().await.expect("row").expect("count row");
+        assert_eq!(row.get::<i64>(0).expect("count"), 0);
+
+        let listed = backend
+            .list_trashed_documents(user_id, None)
+            .await
+            .expect("list trash");
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, trashed.id);
+
+        let before = trashed.deleted_at - chrono::Duration::seconds(1);
+        assert!(
+            backend
+                .purge_trashed_documents(user_id, None, before)
+                .await
+                .expect("purge nothing")
+                .is_empty()
+        );
+        let after = trashed.deleted_at + chrono::Duration::seconds(1);
+        let purged = backend
+            .purge_trashed_documents(user_id, None, after)
+            .await
+            .expect("purge expired");
+        assert_eq!(purged.len(), 1);
+        assert!(
+            backend
+                .get_trashed_document(user_id, None, trashed.id)
+                .await
+                .expect("lookup")
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn content_changes_record_numbered_revisions() {
+        let tmp = tempdir().expect("tempdir");
+        let db_path = tmp.path().join("workspace.db");
+        let backend = LibSqlBackend::new_local(&db_path)
+            .await
+            .expect("local libsql backend");
+        backend.run_migrations().await.expect("run migrations");
+
+        let document = backend
+            .get_or_create_document_by_path("u1", None, "notes/plan.md")
+            .await
+            .expect("create doc");
+        for content in ["step one\n", "step one\n", "step one\nstep two\n"] {
+            backend
+                .update_document(document.id, content, None)
+                .await
+                .expect("write doc");
+        }
+
+        let revisions = backend
+            .list_document_revisions(document.id)
+            .await
+            .expect("list revisions");
+        assert_eq!(revisions.len(), 2, "unchanged writes add no revision");
+        assert_eq!(revisions[0].revision, 2);
+        assert_eq!(revisions[0].diff.as_deref(), Some(" step one\n+step two\n"));
+        assert_eq!(
+            revisions[1].content_hash,
+            history::content_hash("step one\n")
+        );
+        let first = backend
+            .get_document_revision(document.id, 1)
+            .await
+            .expect("get revision")
+            .expect("revision 1");
+        assert_eq!(first.content, "step one\n");
+        assert!(
+            backend
+                .get_document_revision(document.id, 3)
+                .await
+                .expect("get revision")
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn hybrid_search_path_prefix_ranks_vectors_within_the_matter() {
+        let tmp = tempdir().expect("tempdir");
+        let db_path = tmp.path().join("workspace.db");
+        let backend = LibSqlBackend::new_local(&db_path)
+            .await
+            .expect("local libsql backend");
+        backend.run_migrations().await.expect("run migrations");
+
+        let axis = |weights: &[(usize, f32)]| {
+            let mut v = vec![0.0f32; 1536];
+            for &(i, w) in weights {
+                v[i] = w;
+            }
+            v
+        };
+        let query = axis(&[(0, 1.0)]);
+        let user_id = "u1";
+        // More exact matches in another matter than the pre-fusion limit.
+        for n in 0..5 {
+            let document = backend
+                .get_or_create_document_by_path(user_id, None, &format!("matters/other/{n}.md"))
+                .await
+                .expect("create doc");
+            backend
+                .insert_chunk(document.id, 0, "Other matter chunk", Some(&query))
+                .await
+                .expect("insert chunk");
+        }
+        let document = backend
+            .get_or_create_document_by_path(user_id, None, "matters/acme/notes.md")
+            .await
+            .expect("create doc");
+        backend
+            .insert_chunk(
+                document.id,
+                0,
+                "Acme chunk",
+                Some(&axis(&[(0, 0.5), (1, 1.0)])),
+            )
+            .await
+            .expect("insert chunk");
+
+        let config = SearchConfig {
+            use_fts: false,
+            use_vector: true,
+            pre_fusion_limit: 3,
+            limit: 10,
+            rrf_k: 60,
+            min_score: 0.0,
+            path_prefix: None,
+        };
+        let unscoped = backend
+            .hybrid_search(user_id, None, "", Some(&query), &config)
+            .await
+            .expect("unscoped search");
+        assert!(
+            unscoped.iter().all(|r| r.content == "Other matter chunk"),
+            "the global top-k is all foreign chunks"
+        );
+
+        let scoped = backend
+            .hybrid_search(
+                user_id,
+                None,
+                "",
+                Some(&query),
+                &config.with_path_prefix("matters/acme"),
+            )
+            .await
+            .expect("scoped search");
+        assert_eq!(scoped.len(), 1);
+        assert_eq!(scoped[0].content, "Acme chunk");
+    }
 }
@@ -70,6 +70,7 @@ CREATE TABLE IF NOT EXISTS agent_jobs (
     user_id TEXT NOT NULL DEFAULT 'default',
     project_dir TEXT,
     job_mode TEXT NOT NULL DEFAULT 'worker',
+    priority TEXT NOT NULL DEFAULT 'normal',
     budget_amount TEXT,
     budget_token TEXT,
     bid_amount TEXT,
@@ -200,6 +201,7 @@ CREATE TABLE IF NOT EXISTS memory_documents (
     created_at TEXT NOT NULL DEFAULT (datetime('now')),
     updated_at TEXT NOT NULL DEFAULT (datetime('now')),
     metadata TEXT NOT NULL DEFAULT '{}',
+    org_id TEXT REFERENCES organizations(id) ON DELETE SET NULL,
     UNIQUE (user_id, agent_id, path)
 );
 
@@ -406,6 +408,47 @@ CREATE TABLE IF NOT EXISTS tool_failures (
 
 CREATE INDEX IF NOT EXISTS idx_tool_failures_name ON tool_failures(tool_name);
 
+-- ==================== LLM Response Cache ====================
+
+CREATE TABLE IF NOT EXISTS llm_response_cache (
+    cache_key TEXT PRIMARY KEY,
+    model TEXT NOT NULL,
+    response TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    expires_at TEXT NOT NULL,
+    hit_count INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_llm_response_cache_expires ON llm_response_cache(expires_at);
+
+-- ==================== Cost Ledger ====================
+
+CREATE TABLE IF NOT EXISTS cost_entries (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    matter_id TEXT,
+    client_id TEXT,
+    source TEXT NOT NULL CHECK (source IN ('llm_call', 'tool_execution', 'sandbox_job')),
+    job_id TEXT,
+    label TEXT NOT NULL,
+    input_tokens INTEGER NOT NULL DEFAULT 0,
+    output_tokens INTEGER NOT NULL DEFAULT 0,
+    cost TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_cost_entries_user_created ON cost_entries(user_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_cost_entries_user_matter_created
+    ON cost_entries(user_id, matter_id, created_at);
+
+CREATE TABLE IF NOT EXISTS matter_spend_caps (
+    user_id TEXT NOT NULL,
+    matter_id TEXT NOT NULL,
+    monthly_cap_cents INTEGER NOT NULL CHECK (monthly_cap_cents >= 0),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (user_id, matter_id)
+);
+
 -- ==================== Job Events ====================
 
 CREATE TABLE IF NOT EXISTS job_events (
@@ -418,6 +461,65 @@ CREATE TABLE IF NOT EXISTS job_events (
 
 CREATE INDEX IF NOT EXISTS idx_job_events_job ON job_events(job_id, id);
 
+-- ==================== Job Artifacts ====================
+
+CREATE TABLE IF NOT EXISTS job_artifacts (
+    job_id TEXT NOT NULL REFERENCES agent_jobs(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    content_type TEXT NOT NULL,
+    storage_path TEXT NOT NULL,
+    promoted_to TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (job_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_artifacts_created ON job_artifacts(created_at);
+
+-- ==================== Notifications ====================
+
+CREATE TABLE IF NOT EXISTS notifications (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('routine', 'deadline', 'system', 'mention')),
+    severity TEXT NOT NULL DEFAULT 'info' CHECK (severity IN ('info', 'warn', 'critical')),
+    title TEXT NOT NULL,
+    body TEXT NOT NULL DEFAULT '',
+    matter_id TEXT,
+    source TEXT,
+    read_at TEXT,
+    acked_at TEXT,
+    snoozed_until TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_notifications_user_created
+    ON notifications(user_id, created_at);
+
+-- ==================== Document share links ====================
+
+CREATE TABLE IF NOT EXISTS document_share_links (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    path TEXT NOT NULL,
+    matter_id TEXT,
+    token_hash TEXT NOT NULL UNIQUE,
+    password_hash TEXT,
+    recipient TEXT,
+    watermark INTEGER NOT NULL DEFAULT 1,
+    created_by TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    revoked_at TEXT,
+    download_count INTEGER NOT NULL DEFAULT 0,
+    last_downloaded_at TEXT,
+    failed_password_attempts INTEGER NOT NULL DEFAULT 0,
+    last_password_failure_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_document_share_links_user_path
+    ON document_share_links(user_id, path);
+
 -- ==================== Routines ====================
 
 CREATE TABLE IF NOT EXISTS routines (
@@ -445,6 +547,9 @@ CREATE TABLE IF NOT EXISTS routines (
     consecutive_failures INTEGER NOT NULL DEFAULT 0,
     created_at TEXT NOT NULL DEFAULT (datetime('now')),
     updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    urgent INTEGER NOT NULL DEFAULT 0 CHECK (urgent IN (0, 1)),
+    execution_override TEXT NOT NULL DEFAULT '{}',
+    chain TEXT NOT NULL DEFAULT '{}',
     UNIQUE (user_id, name)
 );
 
@@ -536,6 +641,41 @@ CREATE INDEX IF NOT EXISTS idx_matter_parties_party_id ON matter_parties(party_i
 CREATE INDEX IF NOT EXISTS idx_matter_parties_matter_id ON matter_parties(matter_id);
 CREATE INDEX IF NOT EXISTS idx_matter_parties_role_closed_at ON matter_parties(role, closed_at);
 
+CREATE TABLE IF NOT EXISTS party_candidates (
+    id TEXT PRIMARY KEY,
+    matter_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    name_normalized TEXT NOT NULL,
+    entity_type TEXT NOT NULL CHECK (entity_type IN ('person', 'organization')),
+    source_path TEXT NOT NULL,
+    snippet TEXT,
+    confidence REAL NOT NULL DEFAULT 0,
+    extractor TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'accepted', 'rejected')),
+    party_id TEXT REFERENCES parties(id) ON DELETE SET NULL,
+    reviewed_by TEXT,
+    reviewed_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (matter_id, name_normalized, source_path)
+);
+
+CREATE INDEX IF NOT EXISTS idx_party_candidates_matter_status
+    ON party_candidates(matter_id, status, created_at DESC);
+
+CREATE TABLE IF NOT EXISTS party_watchlist (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    name_normalized TEXT NOT NULL,
+    category TEXT NOT NULL DEFAULT 'adverse_party'
+        CHECK (category IN ('adverse_party', 'sanctioned', 'other')),
+    reason TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (user_id, name_normalized)
+);
+
 CREATE TABLE IF NOT EXISTS conflict_clearances (
     id TEXT PRIMARY KEY,
     matter_id TEXT NOT NULL,
@@ -573,6 +713,26 @@ CREATE TABLE IF NOT EXISTS user_tokens (
 
 CREATE INDEX IF NOT EXISTS idx_user_tokens_hash ON user_tokens(token_hash);
 
+CREATE TABLE IF NOT EXISTS organizations (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS organization_members (
+    org_id TEXT NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role TEXT NOT NULL CHECK (role IN ('owner', 'admin', 'member', 'viewer')),
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (org_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_organization_members_user
+    ON organization_members(user_id);
+
 -- ==================== Matter/client core ====================
 
 CREATE TABLE IF NOT EXISTS clients (
@@ -587,6 +747,7 @@ CREATE TABLE IF NOT EXISTS clients (
     notes TEXT,
     created_at TEXT NOT NULL DEFAULT (datetime('now')),
     updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    org_id TEXT REFERENCES organizations(id) ON DELETE SET NULL,
     UNIQUE (user_id, name_normalized)
 );
 
@@ -608,6 +769,7 @@ CREATE TABLE IF NOT EXISTS matters (
     custom_fields TEXT NOT NULL DEFAULT '{}',
     created_at TEXT NOT NULL DEFAULT (datetime('now')),
     updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    org_id TEXT REFERENCES organizations(id) ON DELETE SET NULL,
     PRIMARY KEY (user_id, matter_id)
 );
 
@@ -642,6 +804,9 @@ CREATE TABLE IF NOT EXISTS matter_tasks (
     assignee TEXT,
     due_at TEXT,
     blocked_by TEXT NOT NULL DEFAULT '[]',
+    position INTEGER NOT NULL DEFAULT 0,
+    parent_task_id TEXT,
+    checklist TEXT NOT NULL DEFAULT '[]',
     created_at TEXT NOT NULL DEFAULT (datetime('now')),
     updated_at TEXT NOT NULL DEFAULT (datetime('now')),
     FOREIGN KEY (user_id, matter_id) REFERENCES matters(user_id, matter_id) ON DELETE CASCADE
@@ -657,6 +822,7 @@ CREATE TABLE IF NOT EXISTS matter_notes (
     author TEXT NOT NULL,
     body TEXT NOT NULL,
     pinned INTEGER NOT NULL DEFAULT 0 CHECK (pinned IN (0, 1)),
+    parent_id TEXT,
     created_at TEXT NOT NULL DEFAULT (datetime('now')),
     updated_at TEXT NOT NULL DEFAULT (datetime('now')),
     FOREIGN KEY (user_id, matter_id) REFERENCES matters(user_id, matter_id) ON DELETE CASCADE
@@ -744,6 +910,92 @@ CREATE INDEX IF NOT EXISTS idx_matter_documents_user_matter
 CREATE INDEX IF NOT EXISTS idx_matter_documents_memory_document
     ON matter_documents(memory_document_id);
 
+CREATE TABLE IF NOT EXISTS matter_exhibits (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    matter_id TEXT NOT NULL,
+    party_prefix TEXT NOT NULL,
+    sequence INTEGER NOT NULL CHECK (sequence > 0),
+    label TEXT NOT NULL,
+    path TEXT NOT NULL,
+    matter_document_id TEXT,
+    description TEXT,
+    status TEXT NOT NULL DEFAULT 'marked' CHECK (status IN ('marked', 'withdrawn')),
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    withdrawn_at TEXT,
+    FOREIGN KEY (user_id, matter_id) REFERENCES matters(user_id, matter_id) ON DELETE CASCADE,
+    UNIQUE (user_id, matter_id, party_prefix, sequence),
+    UNIQUE (user_id, matter_id, label)
+);
+
+CREATE TABLE IF NOT EXISTS matter_settlement_proposals (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    matter_id TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('demand', 'offer')),
+    side TEXT NOT NULL CHECK (side IN ('client', 'opposing')),
+    from_party TEXT NOT NULL,
+    to_party TEXT,
+    amount TEXT CHECK (amount IS NULL OR CAST(amount AS REAL) > 0),
+    terms TEXT,
+    proposed_on TEXT NOT NULL,
+    expires_on TEXT,
+    status TEXT NOT NULL DEFAULT 'open' CHECK (status IN (
+        'open',
+        'accepted',
+        'rejected',
+        'countered',
+        'withdrawn',
+        'expired'
+    )),
+    response_notes TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (user_id, matter_id) REFERENCES matters(user_id, matter_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_matter_settlement_proposals_matter
+    ON matter_settlement_proposals(user_id, matter_id, proposed_on);
+
+CREATE TABLE IF NOT EXISTS matter_settlement_authority (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    matter_id TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('minimum', 'maximum')),
+    amount TEXT NOT NULL CHECK (CAST(amount AS REAL) > 0),
+    granted_on TEXT NOT NULL,
+    granted_by TEXT,
+    notes TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (user_id, matter_id) REFERENCES matters(user_id, matter_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_matter_settlement_authority_matter
+    ON matter_settlement_authority(user_id, matter_id, granted_on DESC);
+
+-- ==================== Precedent bank ====================
+
+CREATE TABLE IF NOT EXISTS precedents (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    path TEXT NOT NULL,
+    source_matter_id TEXT NOT NULL,
+    source_path TEXT NOT NULL,
+    document_type TEXT,
+    practice_area TEXT,
+    jurisdiction TEXT,
+    promoted_by TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (user_id, path)
+);
+
+CREATE INDEX IF NOT EXISTS idx_precedents_source_matter
+    ON precedents(user_id, source_matter_id);
+
 CREATE TABLE IF NOT EXISTS document_versions (
     id TEXT PRIMARY KEY,
     user_id TEXT NOT NULL,
@@ -776,6 +1028,52 @@ CREATE TABLE IF NOT EXISTS document_templates (
 
 CREATE INDEX IF NOT EXISTS idx_document_templates_user_matter
     ON document_templates(user_id, matter_id);
+
+CREATE TABLE IF NOT EXISTS document_translations (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    matter_id TEXT NOT NULL,
+    source_path TEXT NOT NULL,
+    source_language TEXT,
+    target_language TEXT NOT NULL,
+    translation_path TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'machine_draft'
+        CHECK (status IN ('machine_draft', 'certification_requested', 'certified')),
+    translator TEXT,
+    certified_path TEXT,
+    certified_at TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (user_id, matter_id, source_path, target_language)
+);
+
+CREATE INDEX IF NOT EXISTS idx_document_translations_user_matter
+    ON document_translations(user_id, matter_id, updated_at DESC);
+
+CREATE TABLE IF NOT EXISTS client_status_reports (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    matter_id TEXT NOT NULL,
+    report_path TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    recipient TEXT,
+    status TEXT NOT NULL DEFAULT 'pending_approval'
+        CHECK (status IN ('pending_approval', 'approved', 'rejected', 'sent')),
+    reviewed_by TEXT,
+    review_note TEXT,
+    reviewed_at TEXT,
+    sent_at TEXT,
+    delivery_error TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (user_id, matter_id, report_path)
+);
+
+CREATE INDEX IF NOT EXISTS idx_client_status_reports_user_matter
+    ON client_status_reports(user_id, matter_id, created_at DESC);
 CREATE INDEX IF NOT EXISTS idx_document_templates_user_name
     ON document_templates(user_id, name);
 
@@ -917,6 +1215,184 @@ CREATE INDEX IF NOT EXISTS idx_audit_events_user_matter_id
 CREATE INDEX IF NOT EXISTS idx_audit_events_user_severity
     ON audit_events(user_id, severity);
 
+-- ==================== SMS consent ====================
+
+CREATE TABLE IF NOT EXISTS sms_consents (
+    user_id TEXT NOT NULL,
+    phone_number TEXT NOT NULL,
+    client_id TEXT REFERENCES clients(id) ON DELETE SET NULL,
+    status TEXT NOT NULL CHECK (status IN ('opted_in', 'opted_out')),
+    source TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (user_id, phone_number)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sms_consents_user_client ON sms_consents(user_id, client_id);
+
+-- ==================== Client communication preferences ====================
+
+CREATE TABLE IF NOT EXISTS client_communication_preferences (
+    user_id TEXT NOT NULL,
+    client_id TEXT NOT NULL REFERENCES clients(id) ON DELETE CASCADE,
+    allowed_channels TEXT NOT NULL DEFAULT '[]',
+    quiet_hours TEXT NOT NULL DEFAULT '[]',
+    timezone TEXT,
+    language TEXT,
+    disclaimers TEXT NOT NULL DEFAULT '[]',
+    updated_by TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (user_id, client_id)
+);
+
+CREATE TABLE IF NOT EXISTS client_communication_consents (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    client_id TEXT NOT NULL REFERENCES clients(id) ON DELETE CASCADE,
+    channel TEXT NOT NULL CHECK (channel IN ('email', 'sms', 'phone', 'mail')),
+    status TEXT NOT NULL CHECK (status IN ('granted', 'revoked')),
+    source TEXT NOT NULL,
+    consented_at TEXT NOT NULL,
+    recorded_by TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_client_communication_consents_client
+    ON client_communication_consents(user_id, client_id, consented_at DESC);
+
+-- ==================== Matter chronology facts ====================
+
+CREATE TABLE IF NOT EXISTS matter_facts (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    matter_id TEXT NOT NULL,
+    fact_date TEXT NOT NULL,
+    description TEXT NOT NULL,
+    source_path TEXT NOT NULL,
+    source_locator TEXT,
+    source_excerpt TEXT,
+    confidence REAL NOT NULL DEFAULT 0 CHECK (confidence >= 0 AND confidence <= 1),
+    extractor TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_matter_facts_user_matter_date
+    ON matter_facts(user_id, matter_id, fact_date);
+CREATE INDEX IF NOT EXISTS idx_matter_facts_user_matter_source
+    ON matter_facts(user_id, matter_id, source_path);
+
+-- ==================== Document entity mentions ====================
+
+CREATE TABLE IF NOT EXISTS document_entities (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    matter_id TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('person', 'organization', 'date', 'amount')),
+    value TEXT NOT NULL,
+    normalized_value TEXT NOT NULL,
+    source_path TEXT NOT NULL,
+    snippet TEXT,
+    confidence REAL NOT NULL DEFAULT 0,
+    extractor TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (user_id, matter_id, source_path, kind, normalized_value)
+);
+
+CREATE INDEX IF NOT EXISTS idx_document_entities_user_matter_kind
+    ON document_entities(user_id, matter_id, kind, normalized_value);
+
+-- ==================== Document tags ====================
+
+CREATE TABLE IF NOT EXISTS document_tags (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    path TEXT NOT NULL,
+    key TEXT NOT NULL,
+    value TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (user_id, path, key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_document_tags_user_key_value
+    ON document_tags(user_id, key, value);
+
+-- ==================== Workspace trash ====================
+
+CREATE TABLE IF NOT EXISTS memory_trash (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    agent_id TEXT,
+    path TEXT NOT NULL,
+    content TEXT NOT NULL,
+    metadata TEXT NOT NULL DEFAULT '{}',
+    deleted_by TEXT NOT NULL,
+    document_created_at TEXT NOT NULL,
+    document_updated_at TEXT NOT NULL,
+    deleted_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_memory_trash_user_deleted
+    ON memory_trash(user_id, deleted_at DESC);
+
+-- ==================== Workspace document history ====================
+
+CREATE TABLE IF NOT EXISTS memory_document_revisions (
+    id TEXT PRIMARY KEY,
+    document_id TEXT NOT NULL REFERENCES memory_documents(id) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    diff TEXT,
+    created_at TEXT NOT NULL,
+    UNIQUE (document_id, revision)
+);
+
+-- ==================== Template library ====================
+
+CREATE TABLE IF NOT EXISTS document_template_library (
+    template_id TEXT PRIMARY KEY REFERENCES document_templates(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL,
+    practice_area TEXT,
+    jurisdiction TEXT,
+    description TEXT,
+    promoted_from TEXT,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_document_template_library_user
+    ON document_template_library(user_id, practice_area, jurisdiction);
+
+CREATE TABLE IF NOT EXISTS document_template_versions (
+    id TEXT PRIMARY KEY,
+    template_id TEXT NOT NULL REFERENCES document_templates(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    body TEXT NOT NULL,
+    variables_json TEXT NOT NULL DEFAULT '[]',
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (template_id, version)
+);
+
+CREATE TABLE IF NOT EXISTS document_template_uses (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    template_id TEXT NOT NULL REFERENCES document_templates(id) ON DELETE CASCADE,
+    matter_id TEXT NOT NULL,
+    document_path TEXT NOT NULL,
+    used_by TEXT NOT NULL,
+    used_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_document_template_uses_user_used_at
+    ON document_template_uses(user_id, used_at DESC);
+
 -- ==================== Missing indexes (parity with PostgreSQL) ====================
 
 -- agent_jobs
@@ -0,0 +1,719 @@
+//! Copy a user's data between database backends (libSQL <-> PostgreSQL).
+//!
+//! Everything goes through the [`Database`] trait, so the copy is
+//! backend-agnostic and reuses the id-preserving upserts from
+//! [`LegalRestoreStore`](crate::db::LegalRestoreStore). Entities are written
+//! in foreign-key order (users and settings, clients, matters, memberships,
+//! workspace documents, then the rows that hang off matters). Ids the target
+//! assigns itself (clients, workspace documents, the primary trust account)
+//! are remapped on the rows that reference them.
+//!
+//! Every write is an upsert, so an interrupted run can simply be re-run.
+//! After copying, both sides are counted with [`count_entities`] and any
+//! entity type where the target holds fewer rows than the source is reported
+//! as a mismatch.
+//!
+//! Not copied: conversation history, jobs and routine runs, the cost ledger,
+//! workspace document revisions and trash, LLM caches, and API token hashes
+//! (tokens must be re-issued on the new backend), nor the practice tables
+//! without a restore upsert yet (party candidates, translations, status
+//! reports, spend caps, SMS consents, matter facts, document entities and tags,
+//! template library metadata, job artifacts, notifications, organization
+//! memberships, share links, the party watchlist, exhibits, settlement
+//! proposals and authority, communication preferences and consents,
+//! precedents). Those source rows are counted and listed in
+//! [`MigrationReport::not_copied`] so the operator can see what stays behind,
+//! and [`MigrationReport::complete`] is false while any remain.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::db::{
+    AuditEventQuery, AuditEventRecord, CreateClientParams, Database, DocumentTemplateRecord,
+    HistoryCounts, UpsertDocumentTemplateParams, UpsertMatterMembershipParams, UpsertMatterParams,
+    UpsertTrustAccountParams,
+};
+use crate::error::{DatabaseError, WorkspaceError};
+use crate::workspace::{ChunkConfig, chunk_document};
+
+/// Page size for audit event copies.
+const AUDIT_PAGE_SIZE: usize = 200;
+
+/// Row counts per entity type for one user.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct EntityCounts {
+    pub settings: usize,
+    pub users: usize,
+    pub clients: usize,
+    pub matters: usize,
+    pub memberships: usize,
+    pub workspace_documents: usize,
+    pub tasks: usize,
+    pub notes: usize,
+    pub deadlines: usize,
+    pub documents: usize,
+    pub document_versions: usize,
+    pub templates: usize,
+    pub time_entries: usize,
+    pub expense_entries: usize,
+    pub invoices: usize,
+    pub invoice_line_items: usize,
+    pub trust_ledger: usize,
+    pub audit_events: usize,
+    pub routines: usize,
+}
+
+impl EntityCounts {
+    /// `(name, count)` pairs in copy order.
+    pub fn rows(&self) -> [(&'static str, usize); 19] {
+        [
+            ("settings", self.settings),
+            ("users", self.users),
+            ("clients", self.clients),
+            ("matters", self.matters),
+            ("memberships", self.memberships),
+            ("workspace_documents", self.workspace_documents),
+            ("tasks", self.tasks),
+            ("notes", self.notes),
+            ("deadlines", self.deadlines),
+            ("documents", self.documents),
+            ("document_versions", self.document_versions),
+            ("templates", self.templates),
+            ("time_entries", self.time_entries),
+            ("expense_entries", self.expense_entries),
+            ("invoices", self.invoices),
+            ("invoice_line_items", self.invoice_line_items),
+            ("trust_ledger", self.trust_ledger),
+            ("audit_events", self.audit_events),
+            ("routines", self.routines),
+        ]
+    }
+}
+
+/// Outcome of [`migrate_backend`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MigrationReport {
+    /// Counts read from the source before copying.
+    pub source: EntityCounts,
+    /// Rows written to the target.
+    pub copied: EntityCounts,
+    /// Counts read back from the target after copying.
+    pub target: EntityCounts,
+    /// Entity types where the target ended up with fewer rows than the source.
+    pub mismatches: Vec<String>,
+    /// Rows that were skipped with the reason.
+    pub warnings: Vec<String>,
+    /// Source rows left behind on the source backend.
+    pub not_copied: HistoryCounts,
+}
+
+impl MigrationReport {
+    /// True when every copied entity type verified. Rows in `not_copied`
+    /// are outside this check.
+    pub fn verified(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    /// True when the source had nothing outside the copied entity types.
+    pub fn complete(&self) -> bool {
+        self.not_copied.total() == 0
+    }
+}
+
+fn ws(e: WorkspaceError) -> DatabaseError {
+    DatabaseError::Query(e.to_string())
+}
+
+/// Templates for a user: the unscoped ones plus every matter's.
+async fn list_all_templates(
+    db: &dyn Database,
+    user_id: &str,
+    matter_ids: &[String],
+) -> Result<Vec<DocumentTemplateRecord>, DatabaseError> {
+    let mut seen = HashSet::new();
+    let mut templates = Vec::new();
+    let scopes = std::iter::once(None).chain(matter_ids.iter().map(|id| Some(id.as_str())));
+    for scope in scopes {
+        for template in db.list_document_templates(user_id, scope).await? {
+            if seen.insert(template.id) {
+                templates.push(template);
+            }
+        }
+    }
+    Ok(templates)
+}
+
+async fn list_all_audit_events(
+    db: &dyn Database,
+    user_id: &str,
+) -> Result<Vec<AuditEventRecord>, DatabaseError> {
+    let query = AuditEventQuery::default();
+    let mut events = Vec::new();
+    loop {
+        let batch = db
+            .list_audit_events(user_id, &query, AUDIT_PAGE_SIZE, events.len())
+            .await?;
+        let done = batch.len() < AUDIT_PAGE_SIZE;
+        events.extend(batch);
+        if done {
+            return Ok(events);
+        }
+    }
+}
+
+/// User ids referenced by `user_id`'s matters: the owner and every member.
+async fn referenced_users(
+    db: &dyn Database,
+    user_id: &str,
+    matter_ids: &[String],
+) -> Result<Vec<String>, DatabaseError> {
+    let mut users = vec![user_id.to_string()];
+    for matter_id in matter_ids {
+        for membership in db.list_matter_memberships(user_id, matter_id).await? {
+            if !users.contains(&membership.member_user_id) {
+                users.push(membership.member_user_id);
+            }
+        }
+    }
+    Ok(users)
+}
+
+/// Count `user_id`'s rows per entity type.
+pub async fn count_entities(
+    db: &dyn Database,
+    user_id: &str,
+) -> Result<EntityCounts, DatabaseError> {
+    let mut counts = EntityCounts {
+        settings: db.get_all_settings(user_id).await?.len(),
+        clients: db.list_clients(user_id, None).await?.len(),
+        workspace_documents: db.list_all_paths(user_id, None).await.map_err(ws)?.len(),
+        audit_events: list_all_audit_events(db, user_id).await?.len(),
+        routines: db.list_routines(user_id).await?.len(),
+        ..Default::default()
+    };
+
+    let matter_ids: Vec<String> = db
+        .list_matters_db(user_id)
+        .await?
+        .into_iter()
+        .map(|m| m.matter_id)
+        .collect();
+    counts.matters = matter_ids.len();
+    counts.templates = list_all_templates(db, user_id, &matter_ids).await?.len();
+
+    for user in referenced_users(db, user_id, &matter_ids).await? {
+        if db.get_user_account(&user).await?.is_some() {
+            counts.users += 1;
+        }
+    }
+
+    for matter_id in &matter_ids {
+        counts.memberships += db.list_matter_memberships(user_id, matter_id).await?.len();
+        counts.tasks += db.list_matter_tasks(user_id, matter_id).await?.len();
+        counts.notes += db.list_matter_notes(user_id, matter_id).await?.len();
+        counts.deadlines += db.list_matter_deadlines(user_id, matter_id).await?.len();
+        for doc in db.list_matter_documents_db(user_id, matter_id).await? {
+            counts.documents += 1;
+            counts.document_versions += db.list_document_versions(user_id, doc.id).await?.len();
+        }
+        counts.time_entries += db.list_time_entries(user_id, matter_id).await?.len();
+        counts.expense_entries += db.list_expense_entries(user_id, matter_id).await?.len();
+        for invoice in db.list_invoices(user_id, Some(matter_id)).await? {
+            counts.invoices += 1;
+            counts.invoice_line_items +=
+                db.list_invoice_line_items(user_id, invoice.id).await?.len();
+        }
+        counts.trust_ledger += db
+            .list_trust_ledger_entries(user_id, matter_id)
+            .await?
+            .len();
+    }
+
+    Ok(counts)
+}
+
+/// Copy `user_id`'s data from `source` to `target`, calling `progress` with
+/// each entity type and the number of rows copied as it finishes.
+///
+/// Both databases must already be migrated to the current schema.
+pub async fn migrate_backend(
+    source: &dyn Database,
+    target: &dyn Database,
+    user_id: &str,
+    progress: &mut (dyn FnMut(&'static str, usize) + Send),
+) -> Result<MigrationReport, DatabaseError> {
+    let mut report = MigrationReport {
+        source: count_entities(source, user_id).await?,
+        not_copied: source.count_history_rows(user_id).await?,
+        ..Default::default()
+    };
+    let copied = &mut report.copied;
+
+    // Settings.
+    let settings = source.get_all_settings(user_id).await?;
+    target.set_all_settings(user_id, &settings).await?;
+    copied.settings = settings.len();
+    progress("settings", copied.settings);
+
+    let matters = source.list_matters_db(user_id).await?;
+    let matter_ids: Vec<String> = matters.iter().map(|m| m.matter_id.clone()).collect();
+
+    // Users: the owner and every matter member, with their role and status.
+    for user in referenced_users(source, user_id, &matter_ids).await? {
+        let Some(account) = source.get_user_account(&user).await? else {
+            continue;
+        };
+        target
+            .ensure_user_account(&account.id, &account.display_name, account.role)
+            .await?;
+        // ensure_user_account keeps an existing row's role; force the source's.
+        target.update_user_role(&account.id, account.role).await?;
+        if !account.is_active {
+            target.deactivate_user(&account.id).await?;
+        }
+        copied.users += 1;
+    }
+    progress("users", copied.users);
+
+    // Clients. The target assigns its own ids; matters are remapped below.
+    let mut client_ids: HashMap<Uuid, Uuid> = HashMap::new();
+    for client in source.list_clients(user_id, None).await? {
+        let restored = target
+            .upsert_client_by_normalized_name(
+                user_id,
+                &CreateClientParams {
+                    name: client.name.clone(),
+                    client_type: client.client_type,
+                    email: client.email.clone(),
+                    phone: client.phone.clone(),
+                    address: client.address.clone(),
+                    notes: client.notes.clone(),
+                },
+            )
+            .await?;
+        client_ids.insert(client.id, restored.id);
+        copied.clients += 1;
+    }
+    progress("clients", copied.clients);
+
+    // Matters.
+    for matter in &matters {
+        let Some(client_id) = client_ids.get(&matter.client_id).copied() else {
+            report.warnings.push(format!(
+                "skipped matter '{}': client {} not found",
+                matter.matter_id, matter.client_id
+            ));
+            continue;
+        };
+        target
+            .upsert_matter(
+                user_id,
+                &UpsertMatterParams {
+                    matter_id: matter.matter_id.clone(),
+                    client_id,
+                    status: matter.status,
+                    stage: matter.stage.clone(),
+                    practice_area: matter.practice_area.clone(),
+                    jurisdiction: matter.jurisdiction.clone(),
+                    opened_at: matter.opened_at,
+                    closed_at: matter.closed_at,
+                    assigned_to: matter.assigned_to.clone(),
+                    custom_fields: matter.custom_fields.clone(),
+                },
+            )
+            .await?;
+        copied.matters += 1;
+    }
+    progress("matters", copied.matters);
+
+    // Matter memberships.
+    for matter_id in &matter_ids {
+        for membership in source.list_matter_memberships(user_id, matter_id).await? {
+            target
+                .upsert_matter_membership(&UpsertMatterMembershipParams {
+                    matter_owner_user_id: membership.matter_owner_user_id,
+                    matter_id: membership.matter_id,
+                    member_user_id: membership.member_user_id,
+                    role: membership.role,
+                })
+                .await?;
+            copied.memberships += 1;
+        }
+    }
+    progress("memberships", copied.memberships);
+
+    // Workspace documents, stored as-is (encrypted matter files stay
+    // encrypted). Plaintext documents are re-chunked for search; embeddings
+    // are left for the usual backfill.
+    let mut memory_doc_ids: HashMap<Uuid, Uuid> = HashMap::new();
+    for path in source.list_all_paths(user_id, None).await.map_err(ws)? {
+        let doc = source
+            .get_document_by_path(user_id, None, &path)
+            .await
+            .map_err(ws)?;
+        let restored = target
+            .get_or_create_document_by_path(user_id, None, &path)
+            .await
+            .map_err(ws)?;
+        target
+            .update_document(restored.id, &doc.content, None)
+            .await
+            .map_err(ws)?;
+        target.delete_chunks(restored.id).await.map_err(ws)?;
+        if !crate::legal::workspace_crypto::is_encrypted_payload(&doc.content) {
+            let chunks = chunk_document(&doc.content, ChunkConfig::default());
+            for (index, chunk) in chunks.iter().enumerate() {
+                target
+                    .insert_chunk(restored.id, index as i32, chunk, None)
+                    .await
+                    .map_err(ws)?;
+            }
+        }
+        memory_doc_ids.insert(doc.id, restored.id);
+        copied.workspace_documents += 1;
+    }
+    progress("workspace_documents", copied.workspace_documents);
+
+    // The primary trust account gets a target-side id; ledger rows follow it.
+    let mut trust_account_ids: HashMap<Uuid, Uuid> = HashMap::new();
+    if let Some(account) = source.get_primary_trust_account(user_id).await? {
+        let restored = target
+            .upsert_primary_trust_account(
+                user_id,
+                &UpsertTrustAccountParams {
+                    name: account.name.clone(),
+                    bank_name: account.bank_name.clone(),
+                    account_number_last4: account.account_number_last4.clone(),
+                },
+            )
+            .await?;
+        trust_account_ids.insert(account.id, restored.id);
+    }
+
+    // Rows that hang off matters.
+    for matter_id in &matter_ids {
+        for row in source.list_matter_tasks(user_id, matter_id).await? {
+            target.upsert_matter_task_record(&row).await?;
+            copied.tasks += 1;
+        }
+        for row in source.list_matter_notes(user_id, matter_id).await? {
+            target.upsert_matter_note_record(&row).await?;
+            copied.notes += 1;
+        }
+        for row in source.list_matter_deadlines(user_id, matter_id).await? {
+            target.upsert_matter_deadline_record(&row).await?;
+            copied.deadlines += 1;
+        }
+        for mut row in source.list_matter_documents_db(user_id, matter_id).await? {
+            let versions = source.list_document_versions(user_id, row.id).await?;
+            if let Some(mapped) = memory_doc_ids.get(&row.memory_document_id) {
+                row.memory_document_id = *mapped;
+            }
+            target.upsert_matter_document_record(&row).await?;
+            copied.documents += 1;
+            for mut version in versions {
+                if let Some(mapped) = memory_doc_ids.get(&version.memory_document_id) {
+                    version.memory_document_id = *mapped;
+                }
+                target.upsert_document_version_record(&version).await?;
+                copied.document_versions += 1;
+            }
+        }
+        for row in source.list_time_entries(user_id, matter_id).await? {
+            target.upsert_time_entry_record(&row).await?;
+            copied.time_entries += 1;
+        }
+        for row in source.list_expense_entries(user_id, matter_id).await? {
+            target.upsert_expense_entry_record(&row).await?;
+            copied.expense_entries += 1;
+        }
+        for invoice in source.list_invoices(user_id, Some(matter_id)).await? {
+            let line_items = source.list_invoice_line_items(user_id, invoice.id).await?;
+            target.upsert_invoice_record(&invoice).await?;
+            copied.invoices += 1;
+            for item in line_items {
+                target.upsert_invoice_line_item_record(&item).await?;
+                copied.invoice_line_items += 1;
+            }
+        }
+        for mut row in source.list_trust_ledger_entries(user_id, matter_id).await? {
+            row.trust_account_id = row
+                .trust_account_id
+                .map(|id| trust_account_ids.get(&id).copied().unwrap_or(id));
+            target.upsert_trust_ledger_entry_record(&row).await?;
+            copied.trust_ledger += 1;
+        }
+    }
+    for (name, count) in [
+        ("tasks", copied.tasks),
+        ("notes", copied.notes),
+        ("deadlines", copied.deadlines),
+        ("documents", copied.documents),
+        ("document_versions", copied.document_versions),
+        ("time_entries", copied.time_entries),
+        ("expense_entries", copied.expense_entries),
+        ("invoices", copied.invoices),
+        ("invoice_line_items", copied.invoice_line_items),
+        ("trust_ledger", copied.trust_ledger),
+    ] {
+        progress(name, count);
+    }
+
+    // Templates.
+    for template in list_all_templates(source, user_id, &matter_ids).await? {
+        target
+            .upsert_document_template(
+                user_id,
+                &UpsertDocumentTemplateParams {
+                    matter_id: template.matter_id,
+                    name: template.name,
+                    body: template.body,
+                    variables_json: template.variables_json,
+                },
+            )
+            .await?;
+        copied.templates += 1;
+    }
+    progress("templates", copied.templates);
+
+    // Audit trail.
+    for event in list_all_audit_events(source, user_id).await? {
+        target.upsert_audit_event_record(&event).await?;
+        copied.audit_events += 1;
+    }
+    progress("audit_events", copied.audit_events);
+
+    // Routines keep their ids; runtime state (last run, failures) comes along.
+    for routine in source.list_routines(user_id).await? {
+        if target.get_routine(routine.id).await?.is_some() {
+            target.update_routine(&routine).await?;
+        } else {
+            target.create_routine(&routine).await?;
+        }
+        copied.routines += 1;
+    }
+    progress("routines", copied.routines);
+
+    report.target = count_entities(target, user_id).await?;
+    report.mismatches = report
+        .source
+        .rows()
+        .into_iter()
+        .zip(report.target.rows())
+        .filter(|((_, source), (_, target))| target < source)
+        .map(|((name, source), (_, target))| {
+            format!("{name}: source has {source}, target has {target}")
+        })
+        .collect();
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{
+        ClientType, CreateMatterFactParams, CreateMatterNoteParams, CreateMatterTaskParams,
+        MatterStatus, MatterTaskStatus,
+    };
+    use crate::testing::test_db;
+
+    async fn seed(db: &dyn Database) {
+        let client = db
+            .create_client(
+                "default",
+                &CreateClientParams {
+                    name: "Acme Corp".to_string(),
+                    client_type: ClientType::Entity,
+                    email: None,
+                    phone: None,
+                    address: None,
+                    notes: None,
+                },
+            )
+            .await
+            .expect("client");
+        db.upsert_matter(
+            "default",
+            &UpsertMatterParams {
+                matter_id: "acme-v-globex".to_string(),
+                client_id: client.id,
+                status: MatterStatus::Active,
+                stage: None,
+                practice_area: Some("litigation".to_string()),
+                jurisdiction: None,
+                opened_at: None,
+                closed_at: None,
+                assigned_to: vec![],
+                custom_fields: serde_json::json!({}),
+            },
+        )
+        .await
+        .expect("matter");
+        db.create_matter_task(
+            "default",
+            "acme-v-globex",
+            &CreateMatterTaskParams {
+                title: "Draft complaint".to_string(),
+                description: None,
+                status: MatterTaskStatus::Todo,
+                assignee: None,
+                due_at: None,
+                blocked_by: vec![],
+                parent_task_id: None,
+                checklist: vec![],
+            },
+        )
+        .await
+        .expect("task");
+        db.create_matter_note(
+            "default",
+            "acme-v-globex",
+            &CreateMatterNoteParams {
+                author: "default".to_string(),
+                body: "Client called about discovery".to_string(),
+                pinned: false,
+                parent_id: None,
+            },
+        )
+        .await
+        .expect("note");
+        db.set_setting("default", "agent.name", &serde_json::json!("clawyer"))
+            .await
+            .expect("setting");
+        let doc = db
+            .get_or_create_document_by_path("default", None, "matters/acme-v-globex/matter.yaml")
+            .await
+            .expect("doc");
+        db.update_document(doc.id, "matter_id: acme-v-globex", None)
+            .await
+            .expect("doc content");
+    }
+
+    #[tokio::test]
+    async fn copies_matters_and_verifies_counts() {
+        let (source, _source_dir) = test_db().await;
+        let (target, _target_dir) = test_db().await;
+        seed(source.as_ref()).await;
+
+        let mut phases = Vec::new();
+        let report = migrate_backend(source.as_ref(), target.as_ref(), "default", &mut |n, c| {
+            phases.push((n, c))
+        })
+        .await
+        .expect("migrate");
+
+        assert!(report.verified(), "mismatches: {:?}", report.mismatches);
+        assert_eq!(
+            report.not_copied,
+            HistoryCounts {
+                document_revisions: 1,
+                ..HistoryCounts::default()
+            },
+            "only the seeded document's revision stays behind"
+        );
+        assert_eq!(report.source.matters, 1);
+        assert_eq!(report.target.matters, 1);
+        assert_eq!(report.target.tasks, 1);
+        assert_eq!(report.target.notes, 1);
+        assert_eq!(report.target.workspace_documents, 1);
+        assert!(phases.contains(&("clients", 1)));
+
+        let matter = target
+            .get_matter_db("default", "acme-v-globex")
+            .await
+            .expect("get")
+            .expect("matter copied");
+        let client = target
+            .get_client("default", matter.client_id)
+            .await
+            .expect("get client")
+            .expect("client remapped");
+        assert_eq!(client.name, "Acme Corp");
+        let doc = target
+            .get_document_by_path("default", None, "matters/acme-v-globex/matter.yaml")
+            .await
+            .expect("doc copied");
+        assert_eq!(doc.content, "matter_id: acme-v-globex");
+    }
+
+    #[tokio::test]
+    async fn rerunning_is_idempotent() {
+        let (source, _source_dir) = test_db().await;
+        let (target, _target_dir) = test_db().await;
+        seed(source.as_ref()).await;
+
+        let mut ignore = |_: &'static str, _: usize| {};
+        migrate_backend(source.as_ref(), target.as_ref(), "default", &mut ignore)
+            .await
+            .expect("first run");
+        let report = migrate_backend(source.as_ref(), target.as_ref(), "default", &mut ignore)
+            .await
+            .expect("second run");
+
+        assert!(report.verified());
+        assert_eq!(report.target, report.source);
+    }
+
+    #[tokio::test]
+    async fn uncopied_rows_are_reported_as_not_copied() {
+        let (source, _source_dir) = test_db().await;
+        let (target, _target_dir) = test_db().await;
+        seed(source.as_ref()).await;
+        let conversation = source
+            .create_conversation_with_metadata("gateway", "default", &serde_json::json!({}))
+            .await
+            .expect("conversation");
+        source
+            .add_conversation_message(conversation, "user", "Summarize the complaint")
+            .await
+            .expect("message");
+        source
+            .create_matter_fact(
+                "default",
+                "acme-v-globex",
+                &CreateMatterFactParams {
+                    fact_date: chrono::NaiveDate::from_ymd_opt(2024, 3, 1).expect("date"),
+                    description: "Supply agreement signed".to_string(),
+                    source_path: "matters/acme-v-globex/matter.yaml".to_string(),
+                    source_locator: None,
+                    source_excerpt: None,
+                    confidence: 1.0,
+                    extractor: "manual".to_string(),
+                    created_by: "default".to_string(),
+                },
+            )
+            .await
+            .expect("fact");
+        source
+            .trash_document_by_path(
+                "default",
+                None,
+                "matters/acme-v-globex/matter.yaml",
+                "default",
+            )
+            .await
+            .expect("trash");
+
+        let mut ignore = |_: &'static str, _: usize| {};
+        let report = migrate_backend(source.as_ref(), target.as_ref(), "default", &mut ignore)
+            .await
+            .expect("migrate");
+
+        assert!(report.verified(), "mismatches: {:?}", report.mismatches);
+        assert!(!report.complete());
+        assert_eq!(report.not_copied.conversations, 1);
+        assert_eq!(report.not_copied.conversation_messages, 1);
+        assert_eq!(report.not_copied.trashed_documents, 1);
+        assert_eq!(report.not_copied.matter_facts, 1);
+        assert_eq!(
+            target
+                .count_history_rows("default")
+                .await
+                .expect("target history"),
+            HistoryCounts::default()
+        );
+    }
+}
@@ -18,6 +18,8 @@ pub mod libsql;
 #[cfg(feature = "libsql")]
 pub mod libsql_migrations;
 
+pub mod migrate;
+
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
@@ -36,7 +38,10 @@ use crate::history::{
     ConversationMessage, ConversationSummary, JobEventRecord, LlmCallRecord, SandboxJobRecord,
     SandboxJobSummary, SettingRow,
 };
-use crate::workspace::{MemoryChunk, MemoryDocument, WorkspaceEntry};
+use crate::workspace::{
+    CitedChunk, DocumentRevision, MemoryChunk, MemoryDocument, TrashedDocument, WorkspaceEntry,
+    WorkspaceStorageUsage,
+};
 use crate::workspace::{SearchConfig, SearchResult};
 
 /// Create a database backend from configuration, run migrations, and return it.
@@ -71,7 +76,16 @@ pub async fn connect_from_config(
                     .await
                     .map_err(|e| DatabaseError::Pool(e.to_string()))?
             };
+            let backend = if config.libsql_encrypt_content {
+                let secrets = crate::config::SecretsConfig::resolve()
+                    .await
+                    .map_err(|e| DatabaseError::Pool(e.to_string()))?;
+                backend.with_content_encryption_from_config(true, secrets.master_key())?
+            } else {
+                backend
+            };
             backend.run_migrations().await?;
+            backend.encrypt_existing_content().await?;
             Ok(Arc::new(backend))
         }
         #[cfg(feature = "postgres")]
@@ -314,6 +328,99 @@ pub struct CreatePartyRelationshipParams {
     pub kind: String,
 }
 
+/// Kind of named entity extracted from an ingested document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartyEntityType {
+    Person,
+    Organization,
+}
+
+impl PartyEntityType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Person => "person",
+            Self::Organization => "organization",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "person" => Some(Self::Person),
+            "organization" => Some(Self::Organization),
+            _ => None,
+        }
+    }
+}
+
+/// Review state of an extracted party candidate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartyCandidateStatus {
+    #[default]
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+impl PartyCandidateStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Accepted => "accepted",
+            Self::Rejected => "rejected",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(Self::Pending),
+            "accepted" => Some(Self::Accepted),
+            "rejected" => Some(Self::Rejected),
+            _ => None,
+        }
+    }
+}
+
+/// Entity extracted from a matter document, queued for review before it
+/// joins the conflict graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartyCandidateRecord {
+    pub id: Uuid,
+    pub matter_id: String,
+    pub name: String,
+    pub entity_type: PartyEntityType,
+    /// Workspace path of the document the entity was found in.
+    pub source_path: String,
+    pub snippet: Option<String>,
+    pub confidence: f64,
+    /// Extractor that produced the candidate (`heuristic` or `llm`).
+    pub extractor: String,
+    pub status: PartyCandidateStatus,
+    pub party_id: Option<Uuid>,
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpsertPartyCandidateParams {
+    pub name: String,
+    pub entity_type: PartyEntityType,
+    pub source_path: String,
+    pub snippet: Option<String>,
+    pub confidence: f64,
+    pub extractor: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReviewPartyCandidateParams {
+    pub status: PartyCandidateStatus,
+    pub reviewed_by: String,
+    pub party_id: Option<Uuid>,
+}
+
 /// Role assigned to a gateway user identity.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -382,6 +489,24 @@ impl MatterMemberRole {
             _ => None,
         }
     }
+
+    /// Role hierarchy rank — higher value = more permissive.
+    pub fn rank(self) -> u8 {
+        match self {
+            Self::Viewer => 1,
+            Self::Collaborator => 2,
+            Self::Owner => 3,
+        }
+    }
+
+    /// Whichever of `self` and `other` grants more access.
+    pub fn stronger(self, other: Self) -> Self {
+        if other.rank() > self.rank() {
+            other
+        } else {
+            self
+        }
+    }
 }
 
 /// Membership row linking a user to a matter.
@@ -404,6 +529,79 @@ pub struct UpsertMatterMembershipParams {
     pub role: MatterMemberRole,
 }
 
+/// Role a user holds in an organization (firm).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrgRole {
+    Owner,
+    Admin,
+    Member,
+    Viewer,
+}
+
+impl OrgRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Owner => "owner",
+            Self::Admin => "admin",
+            Self::Member => "member",
+            Self::Viewer => "viewer",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "owner" => Some(Self::Owner),
+            "admin" => Some(Self::Admin),
+            "member" => Some(Self::Member),
+            "viewer" => Some(Self::Viewer),
+            _ => None,
+        }
+    }
+
+    /// Whether this role may manage members and shared records.
+    pub fn can_manage(self) -> bool {
+        matches!(self, Self::Owner | Self::Admin)
+    }
+
+    /// Access this role grants on matters shared with the organization.
+    /// Matter ownership stays with the owning user.
+    pub fn matter_role(self) -> MatterMemberRole {
+        match self {
+            Self::Viewer => MatterMemberRole::Viewer,
+            Self::Owner | Self::Admin | Self::Member => MatterMemberRole::Collaborator,
+        }
+    }
+}
+
+/// Organization (firm) whose members share matters, clients, and documents.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationRecord {
+    pub id: Uuid,
+    pub name: String,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Membership row linking a user to an organization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationMemberRecord {
+    pub org_id: Uuid,
+    pub user_id: String,
+    pub role: OrgRole,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Matter shared with an organization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationMatterRecord {
+    pub org_id: Uuid,
+    pub matter_owner_user_id: String,
+    pub matter_id: String,
+}
+
 /// Client entity type for conflict and matter tracking.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -587,10 +785,27 @@ pub struct MatterTaskRecord {
     pub assignee: Option<String>,
     pub due_at: Option<DateTime<Utc>>,
     pub blocked_by: Vec<Uuid>,
+    /// Board order within the task's lane; lower sorts first.
+    #[serde(default)]
+    pub position: i64,
+    /// Parent task when this is a subtask. A parent is marked done once all
+    /// of its subtasks are done or cancelled.
+    #[serde(default)]
+    pub parent_task_id: Option<Uuid>,
+    /// Checklist steps in display order.
+    #[serde(default)]
+    pub checklist: Vec<MatterTaskChecklistItem>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatterTaskChecklistItem {
+    pub text: String,
+    #[serde(default)]
+    pub done: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct CreateMatterTaskParams {
     pub title: String,
@@ -599,6 +814,8 @@ pub struct CreateMatterTaskParams {
     pub assignee: Option<String>,
     pub due_at: Option<DateTime<Utc>>,
     pub blocked_by: Vec<Uuid>,
+    pub parent_task_id: Option<Uuid>,
+    pub checklist: Vec<MatterTaskChecklistItem>,
 }
 
 #[derive(Debug, Clone)]
@@ -609,6 +826,18 @@ pub struct UpdateMatterTaskParams {
     pub assignee: Option<Option<String>>,
     pub due_at: Option<Option<DateTime<Utc>>>,
     pub blocked_by: Option<Vec<Uuid>>,
+    pub parent_task_id: Option<Option<Uuid>>,
+    pub checklist: Option<Vec<MatterTaskChecklistItem>>,
+}
+
+/// One card move on the task board. `status` and `assignee` change the
+/// card's lane when set; `position` is its new order within the lane.
+#[derive(Debug, Clone)]
+pub struct MatterTaskMove {
+    pub task_id: Uuid,
+    pub status: Option<MatterTaskStatus>,
+    pub assignee: Option<Option<String>>,
+    pub position: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -619,6 +848,9 @@ pub struct MatterNoteRecord {
     pub author: String,
     pub body: String,
     pub pinned: bool,
+    /// Root note of the thread this note replies to; `None` for top-level notes.
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -628,6 +860,7 @@ pub struct CreateMatterNoteParams {
     pub author: String,
     pub body: String,
     pub pinned: bool,
+    pub parent_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone)]
@@ -946,6 +1179,150 @@ pub struct CreateCitationVerificationResultParams {
     pub waived_at: Option<DateTime<Utc>>,
 }
 
+/// Workflow state of a foreign-language document translation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranslationStatus {
+    /// Machine translation only; not fit for filing.
+    #[default]
+    MachineDraft,
+    /// A certified human translation has been ordered.
+    CertificationRequested,
+    /// A certified human translation is on file and supersedes the draft.
+    Certified,
+}
+
+impl TranslationStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::MachineDraft => "machine_draft",
+            Self::CertificationRequested => "certification_requested",
+            Self::Certified => "certified",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "machine_draft" => Some(Self::MachineDraft),
+            "certification_requested" => Some(Self::CertificationRequested),
+            "certified" => Some(Self::Certified),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentTranslationRecord {
+    pub id: Uuid,
+    pub user_id: String,
+    pub matter_id: String,
+    pub source_path: String,
+    pub source_language: Option<String>,
+    pub target_language: String,
+    /// Workspace path of the machine translation stored beside the source.
+    pub translation_path: String,
+    /// Model that produced the machine translation.
+    pub provider: String,
+    pub status: TranslationStatus,
+    pub translator: Option<String>,
+    pub certified_path: Option<String>,
+    pub certified_at: Option<DateTime<Utc>>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpsertDocumentTranslationParams {
+    pub source_path: String,
+    pub source_language: Option<String>,
+    pub target_language: String,
+    pub translation_path: String,
+    pub provider: String,
+    pub created_by: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpdateDocumentTranslationStatusParams {
+    pub status: TranslationStatus,
+    pub translator: Option<String>,
+    pub certified_path: Option<String>,
+}
+
+/// Approval state of a drafted client status report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientStatusReportStatus {
+    /// Drafted and waiting for an attorney to review it.
+    #[default]
+    PendingApproval,
+    /// Approved by an attorney; delivery is pending or failed.
+    Approved,
+    /// Rejected by an attorney and never sent.
+    Rejected,
+    /// Delivered to the client by the email tool.
+    Sent,
+}
+
+impl ClientStatusReportStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PendingApproval => "pending_approval",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+            Self::Sent => "sent",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "pending_approval" => Some(Self::PendingApproval),
+            "approved" => Some(Self::Approved),
+            "rejected" => Some(Self::Rejected),
+            "sent" => Some(Self::Sent),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientStatusReportRecord {
+    pub id: Uuid,
+    pub user_id: String,
+    pub matter_id: String,
+    /// Workspace path of the drafted report.
+    pub report_path: String,
+    pub subject: String,
+    pub recipient: Option<String>,
+    pub status: ClientStatusReportStatus,
+    pub reviewed_by: Option<String>,
+    pub review_note: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub sent_at: Option<DateTime<Utc>>,
+    /// Last delivery failure reported by the email tool.
+    pub delivery_error: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpsertClientStatusReportParams {
+    pub report_path: String,
+    pub subject: String,
+    pub recipient: Option<String>,
+    pub created_by: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpdateClientStatusReportParams {
+    pub status: ClientStatusReportStatus,
+    pub reviewed_by: Option<String>,
+    pub review_note: Option<String>,
+    pub recipient: Option<String>,
+    pub delivery_error: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DocumentVersionRecord {
     pub id: Uuid,
@@ -992,6 +1369,58 @@ pub struct UpdateDocumentTemplateParams {
     pub variables_json: Option<serde_json::Value>,
 }
 
+/// Shared-library metadata for a firm-wide (non-matter) template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateLibraryEntryRecord {
+    pub template_id: Uuid,
+    pub user_id: String,
+    pub practice_area: Option<String>,
+    pub jurisdiction: Option<String>,
+    pub description: Option<String>,
+    /// Matter template this entry was promoted from, if any.
+    pub promoted_from: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SetTemplateLibraryEntryParams {
+    pub practice_area: Option<String>,
+    pub jurisdiction: Option<String>,
+    pub description: Option<String>,
+    pub promoted_from: Option<Uuid>,
+}
+
+/// Immutable snapshot of a template's name, body, and variable schema.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentTemplateVersionRecord {
+    pub id: Uuid,
+    pub template_id: Uuid,
+    pub version: i32,
+    pub name: String,
+    pub body: String,
+    pub variables_json: serde_json::Value,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordDocumentTemplateUseParams {
+    pub template_id: Uuid,
+    pub matter_id: String,
+    pub document_path: String,
+    pub used_by: String,
+}
+
+/// Per-template generation counts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentTemplateUsageRecord {
+    pub template_id: Uuid,
+    pub uses: i64,
+    /// Distinct matters the template generated documents for.
+    pub matters: i64,
+    pub last_used_at: DateTime<Utc>,
+}
+
 /// Expense category for matter accounting.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -1780,6 +2209,18 @@ pub trait ConversationStore: Send + Sync {
     ) -> Result<Option<String>, DatabaseError>;
 }
 
+/// A completed estimation snapshot: the planned estimate next to what the job
+/// actually cost and how long it took.
+#[derive(Debug, Clone)]
+pub struct EstimationSampleRecord {
+    pub category: String,
+    pub estimated_cost: Decimal,
+    pub actual_cost: Decimal,
+    pub estimated_time_secs: i32,
+    pub actual_time_secs: i32,
+    pub created_at: DateTime<Utc>,
+}
+
 #[async_trait]
 pub trait JobStore: Send + Sync {
     async fn save_job(&self, ctx: &JobContext) -> Result<(), DatabaseError>;
@@ -1811,6 +2252,28 @@ pub trait JobStore: Send + Sync {
         actual_time_secs: i32,
         actual_value: Option<Decimal>,
     ) -> Result<(), DatabaseError>;
+    /// Most recent snapshots with recorded actuals, newest first, optionally
+    /// restricted to one category.
+    async fn list_estimation_samples(
+        &self,
+        category: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<EstimationSampleRecord>, DatabaseError>;
+}
+
+/// A file a sandbox job produced, copied out of its project directory.
+#[derive(Debug, Clone)]
+pub struct JobArtifactRecord {
+    pub job_id: Uuid,
+    /// Path relative to the job's project directory (`/`-separated).
+    pub name: String,
+    pub size_bytes: i64,
+    pub content_type: String,
+    /// Where the copy lives on the host.
+    pub storage_path: String,
+    /// Workspace path the artifact was promoted to, if any.
+    pub promoted_to: Option<String>,
+    pub created_at: DateTime<Utc>,
 }
 
 #[async_trait]
@@ -1844,6 +2307,12 @@ pub trait SandboxStore: Send + Sync {
     ) -> Result<bool, DatabaseError>;
     async fn update_sandbox_job_mode(&self, id: Uuid, mode: &str) -> Result<(), DatabaseError>;
     async fn get_sandbox_job_mode(&self, id: Uuid) -> Result<Option<String>, DatabaseError>;
+    async fn update_sandbox_job_priority(
+        &self,
+        id: Uuid,
+        priority: &str,
+    ) -> Result<(), DatabaseError>;
+    async fn get_sandbox_job_priority(&self, id: Uuid) -> Result<Option<String>, DatabaseError>;
     async fn save_job_event(
         &self,
         job_id: Uuid,
@@ -1855,6 +2324,36 @@ pub trait SandboxStore: Send + Sync {
         job_id: Uuid,
         limit: Option<i64>,
     ) -> Result<Vec<JobEventRecord>, DatabaseError>;
+    /// Events with an id greater than `after_id`, oldest first (at most `limit`).
+    async fn list_job_events_after(
+        &self,
+        job_id: Uuid,
+        after_id: i64,
+        limit: i64,
+    ) -> Result<Vec<JobEventRecord>, DatabaseError>;
+    /// Register an artifact; re-registering a name replaces the earlier row.
+    async fn save_job_artifact(&self, artifact: &JobArtifactRecord) -> Result<(), DatabaseError>;
+    async fn list_job_artifacts(
+        &self,
+        job_id: Uuid,
+    ) -> Result<Vec<JobArtifactRecord>, DatabaseError>;
+    async fn get_job_artifact(
+        &self,
+        job_id: Uuid,
+        name: &str,
+    ) -> Result<Option<JobArtifactRecord>, DatabaseError>;
+    async fn mark_job_artifact_promoted(
+        &self,
+        job_id: Uuid,
+        name: &str,
+        promoted_to: &str,
+    ) -> Result<(), DatabaseError>;
+    /// Delete artifacts registered before `cutoff`, returning the removed rows
+    /// so their stored copies can be cleaned up.
+    async fn delete_job_artifacts_before(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<JobArtifactRecord>, DatabaseError>;
 }
 
 #[async_trait]
@@ -1913,6 +2412,792 @@ pub trait ToolFailureStore: Send + Sync {
     async fn increment_repair_attempts(&self, tool_name: &str) -> Result<(), DatabaseError>;
 }
 
+/// Persisted LLM response, keyed by a hash of its normalized context.
+#[derive(Debug, Clone)]
+pub struct LlmCacheEntry {
+    pub cache_key: String,
+    pub model: String,
+    /// Serialized `CompletionResponse` or `ToolCompletionResponse`.
+    pub response: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub hit_count: i64,
+}
+
+#[async_trait]
+pub trait LlmResponseCacheStore: Send + Sync {
+    /// Unexpired entry for `cache_key`; increments its hit count.
+    async fn get_llm_cache_entry(
+        &self,
+        cache_key: &str,
+    ) -> Result<Option<LlmCacheEntry>, DatabaseError>;
+    /// Insert or replace the entry for `cache_key`, resetting its hit count.
+    async fn put_llm_cache_entry(
+        &self,
+        cache_key: &str,
+        model: &str,
+        response: &serde_json::Value,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), DatabaseError>;
+    /// Delete expired entries, returning how many were removed.
+    async fn purge_expired_llm_cache_entries(&self) -> Result<u64, DatabaseError>;
+}
+
+/// What incurred a cost ledger entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CostSource {
+    LlmCall,
+    ToolExecution,
+    SandboxJob,
+}
+
+impl CostSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::LlmCall => "llm_call",
+            Self::ToolExecution => "tool_execution",
+            Self::SandboxJob => "sandbox_job",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "llm_call" => Some(Self::LlmCall),
+            "tool_execution" => Some(Self::ToolExecution),
+            "sandbox_job" => Some(Self::SandboxJob),
+            _ => None,
+        }
+    }
+}
+
+/// A spend attributed to a user and, when known, a matter and client.
+#[derive(Debug, Clone)]
+pub struct CostEntryRecord {
+    pub id: Uuid,
+    pub user_id: String,
+    pub matter_id: Option<String>,
+    pub client_id: Option<Uuid>,
+    pub source: CostSource,
+    pub job_id: Option<Uuid>,
+    /// Model name for LLM calls, tool name for tool executions.
+    pub label: String,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost: Decimal,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordCostEntryParams {
+    pub user_id: String,
+    pub matter_id: Option<String>,
+    pub client_id: Option<Uuid>,
+    pub source: CostSource,
+    pub job_id: Option<Uuid>,
+    pub label: String,
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    pub cost: Decimal,
+}
+
+/// Monthly spend cap for one matter.
+#[derive(Debug, Clone)]
+pub struct MatterSpendCapRecord {
+    pub user_id: String,
+    pub matter_id: String,
+    pub monthly_cap_cents: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait CostLedgerStore: Send + Sync {
+    async fn record_cost_entry(
+        &self,
+        params: &RecordCostEntryParams,
+    ) -> Result<CostEntryRecord, DatabaseError>;
+    /// Entries for `user_id` created in `[since, until)`, oldest first.
+    async fn list_cost_entries(
+        &self,
+        user_id: &str,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CostEntryRecord>, DatabaseError>;
+    /// Total spend on `matter_id` since `since`.
+    async fn matter_cost_since(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Decimal, DatabaseError>;
+    /// Set the matter's monthly cap; `None` removes it.
+    async fn set_matter_spend_cap(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        monthly_cap_cents: Option<i64>,
+    ) -> Result<(), DatabaseError>;
+    async fn get_matter_spend_cap(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<Option<MatterSpendCapRecord>, DatabaseError>;
+    async fn list_matter_spend_caps(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<MatterSpendCapRecord>, DatabaseError>;
+}
+
+/// What produced an inbox notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// Output of a routine run.
+    Routine,
+    /// A deadline reminder.
+    Deadline,
+    /// Something that needs operator attention (e.g. a dead-lettered job).
+    System,
+    /// A teammate @mentioned the user in a matter note.
+    Mention,
+}
+
+impl NotificationKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Routine => "routine",
+            Self::Deadline => "deadline",
+            Self::System => "system",
+            Self::Mention => "mention",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "routine" => Some(Self::Routine),
+            "deadline" => Some(Self::Deadline),
+            "system" => Some(Self::System),
+            "mention" => Some(Self::Mention),
+            _ => None,
+        }
+    }
+}
+
+/// Which notifications a listing returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationFilter {
+    /// Not acknowledged and not currently snoozed.
+    Active,
+    /// Active and not yet read.
+    Unread,
+    /// Snoozed until a later time.
+    Snoozed,
+    Acked,
+    All,
+}
+
+impl NotificationFilter {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "active" => Some(Self::Active),
+            "unread" => Some(Self::Unread),
+            "snoozed" => Some(Self::Snoozed),
+            "acked" => Some(Self::Acked),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+}
+
+/// An entry in a user's notification inbox.
+#[derive(Debug, Clone)]
+pub struct NotificationRecord {
+    pub id: Uuid,
+    pub user_id: String,
+    pub kind: NotificationKind,
+    pub severity: AuditSeverity,
+    pub title: String,
+    pub body: String,
+    pub matter_id: Option<String>,
+    /// What posted it, e.g. `routine:<id>` or `job:<id>`.
+    pub source: Option<String>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub acked_at: Option<DateTime<Utc>>,
+    pub snoozed_until: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewNotificationParams {
+    pub user_id: String,
+    pub kind: NotificationKind,
+    pub severity: AuditSeverity,
+    pub title: String,
+    pub body: String,
+    pub matter_id: Option<String>,
+    pub source: Option<String>,
+}
+
+#[async_trait]
+pub trait NotificationStore: Send + Sync {
+    async fn create_notification(
+        &self,
+        params: &NewNotificationParams,
+    ) -> Result<NotificationRecord, DatabaseError>;
+    /// Notifications matching `filter` (and `kind`, when set), newest first.
+    async fn list_notifications(
+        &self,
+        user_id: &str,
+        filter: NotificationFilter,
+        kind: Option<NotificationKind>,
+        limit: i64,
+    ) -> Result<Vec<NotificationRecord>, DatabaseError>;
+    /// Unread, unacknowledged, unsnoozed notifications per kind. Kinds with
+    /// none are omitted.
+    async fn count_unread_notifications(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<(NotificationKind, i64)>, DatabaseError>;
+    /// Returns `None` when no such notification belongs to `user_id`.
+    async fn mark_notification_read(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<NotificationRecord>, DatabaseError>;
+    /// Mark every unread notification read. Returns how many changed.
+    async fn mark_all_notifications_read(&self, user_id: &str) -> Result<u64, DatabaseError>;
+    /// Acknowledge (dismiss) a notification; it also counts as read.
+    async fn ack_notification(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<NotificationRecord>, DatabaseError>;
+    /// Hide a notification until `until`, when it returns as unread.
+    /// Acknowledged notifications cannot be snoozed.
+    async fn snooze_notification(
+        &self,
+        user_id: &str,
+        id: Uuid,
+        until: DateTime<Utc>,
+    ) -> Result<Option<NotificationRecord>, DatabaseError>;
+}
+
+/// An external share link for one workspace document.
+///
+/// Only a hash of the URL token is stored; the token itself is shown once
+/// when the link is created.
+#[derive(Debug, Clone)]
+pub struct DocumentShareLinkRecord {
+    pub id: Uuid,
+    pub user_id: String,
+    pub path: String,
+    pub matter_id: Option<String>,
+    pub token_hash: String,
+    /// `pbkdf2-sha256$<iterations>$<salt hex>$<digest hex>`; `None` for
+    /// links without a password.
+    pub password_hash: Option<String>,
+    /// Free-form note on who the link was sent to, e.g. co-counsel.
+    pub recipient: Option<String>,
+    pub watermark: bool,
+    pub created_by: String,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub download_count: i64,
+    pub last_downloaded_at: Option<DateTime<Utc>>,
+    /// Password attempts since the last successful download; each is
+    /// counted before it is checked.
+    pub failed_password_attempts: i64,
+    pub last_password_failure_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DocumentShareLinkRecord {
+    /// Not revoked and not yet expired.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NewDocumentShareLinkParams {
+    pub user_id: String,
+    pub path: String,
+    pub matter_id: Option<String>,
+    pub token_hash: String,
+    pub password_hash: Option<String>,
+    pub recipient: Option<String>,
+    pub watermark: bool,
+    pub created_by: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait DocumentShareStore: Send + Sync {
+    async fn create_document_share_link(
+        &self,
+        params: &NewDocumentShareLinkParams,
+    ) -> Result<DocumentShareLinkRecord, DatabaseError>;
+    /// Look a link up by the hash of its URL token, whatever its state.
+    async fn get_document_share_link_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<DocumentShareLinkRecord>, DatabaseError>;
+    /// Links for `user_id`, newest first, optionally limited to one path.
+    async fn list_document_share_links(
+        &self,
+        user_id: &str,
+        path: Option<&str>,
+    ) -> Result<Vec<DocumentShareLinkRecord>, DatabaseError>;
+    async fn get_document_share_link(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<DocumentShareLinkRecord>, DatabaseError>;
+    /// Revoke a link. Returns `None` when no such link belongs to `user_id`.
+    async fn revoke_document_share_link(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<DocumentShareLinkRecord>, DatabaseError>;
+    /// Bump the download counter and last-download time, and clear the
+    /// wrong-password count.
+    async fn record_document_share_download(&self, id: Uuid) -> Result<(), DatabaseError>;
+    /// Count a password attempt against a link before it is checked; a
+    /// download clears the count. Only claims the attempt while the link
+    /// still has `seen_attempts` failures, so concurrent guesses are counted
+    /// one at a time. Returns the updated link, or `None` when the link is
+    /// unknown or another attempt claimed that count first.
+    async fn claim_document_share_password_attempt(
+        &self,
+        id: Uuid,
+        seen_attempts: i64,
+    ) -> Result<Option<DocumentShareLinkRecord>, DatabaseError>;
+}
+
+/// SMS consent state of a phone number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmsConsentStatus {
+    OptedIn,
+    OptedOut,
+}
+
+impl SmsConsentStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::OptedIn => "opted_in",
+            Self::OptedOut => "opted_out",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "opted_in" => Some(Self::OptedIn),
+            "opted_out" => Some(Self::OptedOut),
+            _ => None,
+        }
+    }
+}
+
+/// Latest SMS consent decision for one phone number.
+#[derive(Debug, Clone)]
+pub struct SmsConsentRecord {
+    pub user_id: String,
+    /// E.164 phone number (e.g. `+15551234567`).
+    pub phone_number: String,
+    /// Client whose phone number this is, when one matches.
+    pub client_id: Option<Uuid>,
+    pub status: SmsConsentStatus,
+    /// What recorded the decision, e.g. the keyword `STOP`.
+    pub source: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SetSmsConsentParams {
+    pub phone_number: String,
+    pub client_id: Option<Uuid>,
+    pub status: SmsConsentStatus,
+    pub source: String,
+}
+
+#[async_trait]
+pub trait SmsConsentStore: Send + Sync {
+    async fn get_sms_consent(
+        &self,
+        user_id: &str,
+        phone_number: &str,
+    ) -> Result<Option<SmsConsentRecord>, DatabaseError>;
+    /// Insert or replace the consent record for the phone number.
+    async fn set_sms_consent(
+        &self,
+        user_id: &str,
+        params: &SetSmsConsentParams,
+    ) -> Result<SmsConsentRecord, DatabaseError>;
+    /// All consent records for `user_id`, most recently updated first.
+    async fn list_sms_consents(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<SmsConsentRecord>, DatabaseError>;
+}
+
+/// Channel a client-facing message can be sent over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommunicationChannel {
+    Email,
+    Sms,
+    Phone,
+    Mail,
+}
+
+impl CommunicationChannel {
+    pub const ALL: [Self; 4] = [Self::Email, Self::Sms, Self::Phone, Self::Mail];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Email => "email",
+            Self::Sms => "sms",
+            Self::Phone => "phone",
+            Self::Mail => "mail",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "email" => Some(Self::Email),
+            "sms" => Some(Self::Sms),
+            "phone" => Some(Self::Phone),
+            "mail" => Some(Self::Mail),
+            _ => None,
+        }
+    }
+}
+
+/// How a client wants to be contacted. Quiet hours are read in `timezone`.
+#[derive(Debug, Clone)]
+pub struct ClientCommunicationPreferencesRecord {
+    pub user_id: String,
+    pub client_id: Uuid,
+    pub allowed_channels: Vec<CommunicationChannel>,
+    pub quiet_hours: Vec<crate::agent::execution_window::ExecutionWindow>,
+    /// IANA timezone; UTC when unset.
+    pub timezone: Option<String>,
+    /// Language client-facing messages are written in (e.g. `es`).
+    pub language: Option<String>,
+    /// Text appended to every client-facing message.
+    pub disclaimers: Vec<String>,
+    pub updated_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpsertClientCommunicationPreferencesParams {
+    pub allowed_channels: Vec<CommunicationChannel>,
+    pub quiet_hours: Vec<crate::agent::execution_window::ExecutionWindow>,
+    pub timezone: Option<String>,
+    pub language: Option<String>,
+    pub disclaimers: Vec<String>,
+    pub updated_by: String,
+}
+
+/// Whether a client gave or withdrew consent to be contacted on a channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommunicationConsentStatus {
+    Granted,
+    Revoked,
+}
+
+impl CommunicationConsentStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Granted => "granted",
+            Self::Revoked => "revoked",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "granted" => Some(Self::Granted),
+            "revoked" => Some(Self::Revoked),
+            _ => None,
+        }
+    }
+}
+
+/// One consent decision. Decisions are never edited; the latest one per
+/// channel (by `consented_at`) is in force.
+#[derive(Debug, Clone)]
+pub struct ClientCommunicationConsentRecord {
+    pub id: Uuid,
+    pub user_id: String,
+    pub client_id: Uuid,
+    pub channel: CommunicationChannel,
+    pub status: CommunicationConsentStatus,
+    /// Where the decision came from, e.g. `engagement letter` or `sms:STOP`.
+    pub source: String,
+    /// When the client gave or withdrew consent.
+    pub consented_at: DateTime<Utc>,
+    pub recorded_by: String,
+    /// When the decision was entered.
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateClientCommunicationConsentParams {
+    pub channel: CommunicationChannel,
+    pub status: CommunicationConsentStatus,
+    pub source: String,
+    pub consented_at: DateTime<Utc>,
+    pub recorded_by: String,
+}
+
+#[async_trait]
+pub trait ClientCommunicationStore: Send + Sync {
+    async fn get_client_communication_preferences(
+        &self,
+        user_id: &str,
+        client_id: Uuid,
+    ) -> Result<Option<ClientCommunicationPreferencesRecord>, DatabaseError>;
+    /// Insert or replace the client's preferences.
+    async fn upsert_client_communication_preferences(
+        &self,
+        user_id: &str,
+        client_id: Uuid,
+        input: &UpsertClientCommunicationPreferencesParams,
+    ) -> Result<ClientCommunicationPreferencesRecord, DatabaseError>;
+    async fn create_client_communication_consent(
+        &self,
+        user_id: &str,
+        client_id: Uuid,
+        input: &CreateClientCommunicationConsentParams,
+    ) -> Result<ClientCommunicationConsentRecord, DatabaseError>;
+    /// Consent decisions for a client, newest first.
+    async fn list_client_communication_consents(
+        &self,
+        user_id: &str,
+        client_id: Uuid,
+    ) -> Result<Vec<ClientCommunicationConsentRecord>, DatabaseError>;
+}
+
+/// A dated fact extracted from a matter document, with its source citation.
+#[derive(Debug, Clone)]
+pub struct MatterFactRecord {
+    pub id: Uuid,
+    pub user_id: String,
+    pub matter_id: String,
+    pub fact_date: NaiveDate,
+    pub description: String,
+    /// Workspace path of the document the fact came from.
+    pub source_path: String,
+    /// Where in the source the fact appears, e.g. `line 12`.
+    pub source_locator: Option<String>,
+    /// Verbatim text supporting the fact.
+    pub source_excerpt: Option<String>,
+    /// Extractor confidence in `[0, 1]`.
+    pub confidence: f64,
+    /// `heuristic` or `llm`.
+    pub extractor: String,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateMatterFactParams {
+    pub fact_date: NaiveDate,
+    pub description: String,
+    pub source_path: String,
+    pub source_locator: Option<String>,
+    pub source_excerpt: Option<String>,
+    pub confidence: f64,
+    pub extractor: String,
+    pub created_by: String,
+}
+
+/// Filters for [`FactStore::list_matter_facts`]. Date bounds are inclusive.
+#[derive(Debug, Clone, Default)]
+pub struct MatterFactQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub source_path: Option<String>,
+    pub min_confidence: Option<f64>,
+}
+
+#[async_trait]
+pub trait FactStore: Send + Sync {
+    async fn create_matter_fact(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        params: &CreateMatterFactParams,
+    ) -> Result<MatterFactRecord, DatabaseError>;
+    /// Facts matching `query`, in chronological order.
+    async fn list_matter_facts(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        query: &MatterFactQuery,
+    ) -> Result<Vec<MatterFactRecord>, DatabaseError>;
+    /// Remove every fact extracted from `source_path`; returns how many were deleted.
+    async fn delete_matter_facts_for_source(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        source_path: &str,
+    ) -> Result<u64, DatabaseError>;
+}
+
+/// Kind of entity mentioned in a matter document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentEntityKind {
+    Person,
+    Organization,
+    Date,
+    Amount,
+}
+
+impl DocumentEntityKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Person => "person",
+            Self::Organization => "organization",
+            Self::Date => "date",
+            Self::Amount => "amount",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "person" => Some(Self::Person),
+            "organization" => Some(Self::Organization),
+            "date" => Some(Self::Date),
+            "amount" => Some(Self::Amount),
+            _ => None,
+        }
+    }
+
+    /// People and organizations can become parties; dates and amounts cannot.
+    pub fn is_party(self) -> bool {
+        matches!(self, Self::Person | Self::Organization)
+    }
+}
+
+impl From<PartyEntityType> for DocumentEntityKind {
+    fn from(value: PartyEntityType) -> Self {
+        match value {
+            PartyEntityType::Person => Self::Person,
+            PartyEntityType::Organization => Self::Organization,
+        }
+    }
+}
+
+/// An entity mention recorded against the document it was found in.
+#[derive(Debug, Clone)]
+pub struct DocumentEntityRecord {
+    pub id: Uuid,
+    pub user_id: String,
+    pub matter_id: String,
+    pub kind: DocumentEntityKind,
+    /// The entity as written in the document.
+    pub value: String,
+    /// Comparison key: normalized party name, ISO date, or `CUR 1234.56`.
+    pub normalized_value: String,
+    pub source_path: String,
+    pub snippet: Option<String>,
+    pub confidence: f64,
+    /// Extractor that produced the mention (`heuristic` or `llm`).
+    pub extractor: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateDocumentEntityParams {
+    pub kind: DocumentEntityKind,
+    pub value: String,
+    pub normalized_value: String,
+    pub snippet: Option<String>,
+    pub confidence: f64,
+    pub extractor: String,
+}
+
+#[async_trait]
+pub trait DocumentEntityStore: Send + Sync {
+    /// Replace every entity recorded for `source_path` with `entities`.
+    async fn replace_document_entities(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        source_path: &str,
+        entities: &[CreateDocumentEntityParams],
+    ) -> Result<usize, DatabaseError>;
+    /// Entities recorded for a matter, ordered by kind, normalized value, then document.
+    async fn list_document_entities(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        kind: Option<DocumentEntityKind>,
+    ) -> Result<Vec<DocumentEntityRecord>, DatabaseError>;
+}
+
+/// A key/value tag on a workspace document.
+#[derive(Debug, Clone)]
+pub struct DocumentTagRecord {
+    pub id: Uuid,
+    pub user_id: String,
+    pub path: String,
+    /// Normalized key (`hot-doc`, `responsive`, `privilege`).
+    pub key: String,
+    /// `None` for flag tags.
+    pub value: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SetDocumentTagParams {
+    pub key: String,
+    pub value: Option<String>,
+}
+
+#[async_trait]
+pub trait DocumentTagStore: Send + Sync {
+    /// Set `tags` on `path`, replacing the value of keys already present.
+    /// Returns every tag on the document, ordered by key.
+    async fn set_document_tags(
+        &self,
+        user_id: &str,
+        path: &str,
+        tags: &[SetDocumentTagParams],
+        created_by: &str,
+    ) -> Result<Vec<DocumentTagRecord>, DatabaseError>;
+    /// Remove one tag; `false` when the document did not carry it.
+    async fn remove_document_tag(
+        &self,
+        user_id: &str,
+        path: &str,
+        key: &str,
+    ) -> Result<bool, DatabaseError>;
+    /// Tags on one document, ordered by key.
+    async fn list_document_tags(
+        &self,
+        user_id: &str,
+        path: &str,
+    ) -> Result<Vec<DocumentTagRecord>, DatabaseError>;
+    /// Every tag on documents under `path_prefix` (all documents when
+    /// `None`), ordered by path then key.
+    async fn list_tagged_documents(
+        &self,
+        user_id: &str,
+        path_prefix: Option<&str>,
+    ) -> Result<Vec<DocumentTagRecord>, DatabaseError>;
+}
+
 #[async_trait]
 pub trait LegalConflictStore: Send + Sync {
     async fn find_conflict_hits_for_names(
@@ -1971,6 +3256,102 @@ pub trait LegalConflictStore: Send + Sync {
         &self,
         input: &CreatePartyRelationshipParams,
     ) -> Result<PartyRelationshipRecord, DatabaseError>;
+    /// Queue an extracted entity for review. Re-extracting the same name from
+    /// the same document refreshes the snippet/confidence but never reopens
+    /// a candidate that was already reviewed.
+    async fn upsert_party_candidate(
+        &self,
+        matter_id: &str,
+        input: &UpsertPartyCandidateParams,
+    ) -> Result<PartyCandidateRecord, DatabaseError>;
+    async fn list_party_candidates(
+        &self,
+        matter_id: &str,
+        status: Option<PartyCandidateStatus>,
+    ) -> Result<Vec<PartyCandidateRecord>, DatabaseError>;
+    async fn get_party_candidate(
+        &self,
+        matter_id: &str,
+        candidate_id: Uuid,
+    ) -> Result<Option<PartyCandidateRecord>, DatabaseError>;
+    /// Record the review decision on a pending candidate. Returns `None`
+    /// when the candidate is unknown or was already reviewed.
+    async fn review_party_candidate(
+        &self,
+        matter_id: &str,
+        candidate_id: Uuid,
+        input: &ReviewPartyCandidateParams,
+    ) -> Result<Option<PartyCandidateRecord>, DatabaseError>;
+}
+
+/// Why a party is on the watchlist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchlistCategory {
+    /// Frequent or notable adverse party.
+    AdverseParty,
+    /// Sanctioned or otherwise restricted entity.
+    Sanctioned,
+    Other,
+}
+
+impl WatchlistCategory {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::AdverseParty => "adverse_party",
+            Self::Sanctioned => "sanctioned",
+            Self::Other => "other",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "adverse_party" => Some(Self::AdverseParty),
+            "sanctioned" => Some(Self::Sanctioned),
+            "other" => Some(Self::Other),
+            _ => None,
+        }
+    }
+}
+
+/// A party flagged for ongoing monitoring. New clients, matters, and
+/// ingested documents are screened against these names.
+#[derive(Debug, Clone)]
+pub struct WatchlistEntryRecord {
+    pub id: Uuid,
+    pub user_id: String,
+    pub name: String,
+    /// `normalize_party_name(name)`; unique per user.
+    pub name_normalized: String,
+    pub category: WatchlistCategory,
+    pub reason: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewWatchlistEntryParams {
+    pub user_id: String,
+    pub name: String,
+    pub category: WatchlistCategory,
+    pub reason: Option<String>,
+    pub created_by: String,
+}
+
+#[async_trait]
+pub trait PartyWatchlistStore: Send + Sync {
+    /// Add a party, or update the category and reason of the entry with the
+    /// same normalized name.
+    async fn upsert_watchlist_entry(
+        &self,
+        params: &NewWatchlistEntryParams,
+    ) -> Result<WatchlistEntryRecord, DatabaseError>;
+    /// Entries for `user_id`, ordered by name.
+    async fn list_watchlist_entries(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<WatchlistEntryRecord>, DatabaseError>;
+    async fn delete_watchlist_entry(&self, user_id: &str, id: Uuid) -> Result<bool, DatabaseError>;
 }
 
 #[async_trait]
@@ -2004,8 +3385,10 @@ pub trait RbacStore: Send + Sync {
     /// Check whether `requesting_user_id` has access to a matter.
     ///
     /// Returns `Some(MatterMemberRole::Owner)` immediately when the requester is
-    /// the matter owner (no DB query). Otherwise queries `matter_memberships` and
-    /// returns the stored role, or `None` if no membership row exists.
+    /// the matter owner (no DB query). Otherwise returns the stronger of the
+    /// role stored in `matter_memberships` and, for a matter shared with an
+    /// organization, the member's [`OrgRole::matter_role`], so a matter
+    /// membership never demotes an org member. `None` means no access.
     async fn check_matter_access(
         &self,
         matter_owner_user_id: &str,
@@ -2029,6 +3412,90 @@ pub trait RbacStore: Send + Sync {
     async fn deactivate_user(&self, user_id: &str) -> Result<(), DatabaseError>;
 }
 
+/// Organization layer: firms whose members share matters, clients, and
+/// workspace documents. Records keep their owning `user_id`; `org_id` marks
+/// them as shared. Settings stay per user. An organization always keeps at
+/// least one owner.
+#[async_trait]
+pub trait OrganizationStore: Send + Sync {
+    /// Create an organization with `created_by` as its owner member.
+    async fn create_organization(
+        &self,
+        name: &str,
+        created_by: &str,
+    ) -> Result<OrganizationRecord, DatabaseError>;
+    async fn get_organization(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Option<OrganizationRecord>, DatabaseError>;
+    /// Organizations `user_id` belongs to, with their role in each.
+    async fn list_organizations_for_user(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<(OrganizationRecord, OrgRole)>, DatabaseError>;
+    /// Add a member or change their role. Fails with
+    /// [`DatabaseError::Constraint`] instead of demoting the last owner; the
+    /// check and the write are atomic.
+    async fn upsert_organization_member(
+        &self,
+        org_id: Uuid,
+        user_id: &str,
+        role: OrgRole,
+    ) -> Result<OrganizationMemberRecord, DatabaseError>;
+    async fn list_organization_members(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Vec<OrganizationMemberRecord>, DatabaseError>;
+    async fn get_organization_role(
+        &self,
+        org_id: Uuid,
+        user_id: &str,
+    ) -> Result<Option<OrgRole>, DatabaseError>;
+    /// Returns `false` if the user was not a member. Fails with
+    /// [`DatabaseError::Constraint`] instead of removing the last owner.
+    async fn remove_organization_member(
+        &self,
+        org_id: Uuid,
+        user_id: &str,
+    ) -> Result<bool, DatabaseError>;
+    /// Share a matter with an organization (`None` makes it private again).
+    /// Returns `false` if the matter does not exist.
+    async fn set_matter_organization(
+        &self,
+        matter_owner_user_id: &str,
+        matter_id: &str,
+        org_id: Option<Uuid>,
+    ) -> Result<bool, DatabaseError>;
+    async fn get_matter_organization(
+        &self,
+        matter_owner_user_id: &str,
+        matter_id: &str,
+    ) -> Result<Option<Uuid>, DatabaseError>;
+    /// Returns `false` if the client does not exist.
+    async fn set_client_organization(
+        &self,
+        user_id: &str,
+        client_id: Uuid,
+        org_id: Option<Uuid>,
+    ) -> Result<bool, DatabaseError>;
+    /// Tag workspace documents under `path_prefix` with an organization.
+    /// Returns the number of documents updated.
+    async fn set_documents_organization(
+        &self,
+        user_id: &str,
+        path_prefix: &str,
+        org_id: Option<Uuid>,
+    ) -> Result<u64, DatabaseError>;
+    async fn list_organization_matters(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Vec<OrganizationMatterRecord>, DatabaseError>;
+    async fn list_organization_clients(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Vec<ClientRecord>, DatabaseError>;
+}
+
 #[async_trait]
 pub trait ClientStore: Send + Sync {
     async fn create_client(
@@ -2095,6 +3562,8 @@ pub trait MatterTaskStore: Send + Sync {
         matter_id: &str,
         input: &CreateMatterTaskParams,
     ) -> Result<MatterTaskRecord, DatabaseError>;
+    /// Update a task, then mark its ancestors done when every subtask under
+    /// them is done or cancelled.
     async fn update_matter_task(
         &self,
         user_id: &str,
@@ -2102,12 +3571,22 @@ pub trait MatterTaskStore: Send + Sync {
         task_id: Uuid,
         input: &UpdateMatterTaskParams,
     ) -> Result<Option<MatterTaskRecord>, DatabaseError>;
+    /// Delete a task. Its subtasks are kept and become top-level tasks.
     async fn delete_matter_task(
         &self,
         user_id: &str,
         matter_id: &str,
         task_id: Uuid,
     ) -> Result<bool, DatabaseError>;
+    /// Apply board moves in one transaction. Returns `false`, changing
+    /// nothing, when any task is not on the matter. Parents roll up as in
+    /// `update_matter_task`.
+    async fn move_matter_tasks(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        moves: &[MatterTaskMove],
+    ) -> Result<bool, DatabaseError>;
 }
 
 #[async_trait]
@@ -2176,96 +3655,501 @@ pub trait MatterDeadlineStore: Send + Sync {
         &self,
         user_id: &str,
         matter_id: &str,
-        trigger_deadline_id: Uuid,
-    ) -> Result<Vec<MatterDeadlineRecord>, DatabaseError>;
-    /// Apply a manual override: update `due_at`, set override flags, and write
-    /// an immutable row to `deadline_override_audit`.
-    async fn apply_deadline_override(
+        trigger_deadline_id: Uuid,
+    ) -> Result<Vec<MatterDeadlineRecord>, DatabaseError>;
+    /// Apply a manual override: update `due_at`, set override flags, and write
+    /// an immutable row to `deadline_override_audit`.
+    async fn apply_deadline_override(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        deadline_id: Uuid,
+        params: &OverrideDeadlineParams,
+    ) -> Result<MatterDeadlineRecord, DatabaseError>;
+    /// List the complete override audit trail for a deadline, newest first.
+    async fn list_deadline_override_audit(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        deadline_id: Uuid,
+    ) -> Result<Vec<DeadlineOverrideAuditRecord>, DatabaseError>;
+}
+
+#[async_trait]
+pub trait MatterDocumentStore: Send + Sync {
+    async fn list_matter_documents_db(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<Vec<MatterDocumentRecord>, DatabaseError>;
+    async fn get_matter_document(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        matter_document_id: Uuid,
+    ) -> Result<Option<MatterDocumentRecord>, DatabaseError>;
+    async fn get_matter_document_by_id(
+        &self,
+        user_id: &str,
+        matter_document_id: Uuid,
+    ) -> Result<Option<MatterDocumentRecord>, DatabaseError>;
+    async fn upsert_matter_document(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        input: &UpsertMatterDocumentParams,
+    ) -> Result<MatterDocumentRecord, DatabaseError>;
+    async fn update_matter_document(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        matter_document_id: Uuid,
+        input: &UpdateMatterDocumentParams,
+    ) -> Result<Option<MatterDocumentRecord>, DatabaseError>;
+    async fn delete_matter_document(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        matter_document_id: Uuid,
+    ) -> Result<bool, DatabaseError>;
+}
+
+#[async_trait]
+pub trait CitationVerificationStore: Send + Sync {
+    async fn create_citation_verification_run(
+        &self,
+        user_id: &str,
+        input: &CreateCitationVerificationRunParams,
+        results: &[CreateCitationVerificationResultParams],
+    ) -> Result<
+        (
+            CitationVerificationRunRecord,
+            Vec<CitationVerificationResultRecord>,
+        ),
+        DatabaseError,
+    >;
+    async fn latest_citation_verification_run(
+        &self,
+        user_id: &str,
+        matter_document_id: Uuid,
+    ) -> Result<Option<CitationVerificationRunRecord>, DatabaseError>;
+    async fn list_citation_verification_results(
+        &self,
+        user_id: &str,
+        matter_document_id: Uuid,
+    ) -> Result<Vec<CitationVerificationResultRecord>, DatabaseError>;
+    async fn set_matter_document_readiness(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        matter_document_id: Uuid,
+        state: DocumentReadinessState,
+    ) -> Result<Option<MatterDocumentRecord>, DatabaseError>;
+}
+
+#[async_trait]
+pub trait DocumentTranslationStore: Send + Sync {
+    /// Record a machine translation. Re-translating the same source into the
+    /// same language refreshes the draft but keeps the workflow state.
+    async fn upsert_document_translation(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        input: &UpsertDocumentTranslationParams,
+    ) -> Result<DocumentTranslationRecord, DatabaseError>;
+    async fn list_document_translations(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<Vec<DocumentTranslationRecord>, DatabaseError>;
+    async fn get_document_translation(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        translation_id: Uuid,
+    ) -> Result<Option<DocumentTranslationRecord>, DatabaseError>;
+    /// Move a translation through the certification workflow. `certified_at`
+    /// is stamped when the status becomes `Certified`.
+    async fn update_document_translation_status(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        translation_id: Uuid,
+        input: &UpdateDocumentTranslationStatusParams,
+    ) -> Result<Option<DocumentTranslationRecord>, DatabaseError>;
+}
+
+/// Whether an exhibit is still part of the record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExhibitStatus {
+    Marked,
+    /// Withdrawn exhibits keep their label so numbering stays stable.
+    Withdrawn,
+}
+
+impl ExhibitStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Marked => "marked",
+            Self::Withdrawn => "withdrawn",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "marked" => Some(Self::Marked),
+            "withdrawn" => Some(Self::Withdrawn),
+            _ => None,
+        }
+    }
+}
+
+/// A matter document marked as an exhibit, numbered per party prefix.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatterExhibitRecord {
+    pub id: Uuid,
+    pub user_id: String,
+    pub matter_id: String,
+    /// Party prefix such as `P`, `D`, or `J`.
+    pub party_prefix: String,
+    /// 1-based position within the prefix; never reused.
+    pub sequence: i32,
+    /// `{party_prefix}-{sequence}`, e.g. `P-3`.
+    pub label: String,
+    /// Workspace path of the exhibit document.
+    pub path: String,
+    pub matter_document_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub status: ExhibitStatus,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub withdrawn_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateMatterExhibitParams {
+    pub party_prefix: String,
+    pub path: String,
+    pub matter_document_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub created_by: String,
+}
+
+#[async_trait]
+pub trait MatterExhibitStore: Send + Sync {
+    /// Mark an exhibit with the next number for its party prefix.
+    async fn create_matter_exhibit(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        input: &CreateMatterExhibitParams,
+    ) -> Result<MatterExhibitRecord, DatabaseError>;
+    /// Exhibits for a matter, ordered by prefix and number.
+    async fn list_matter_exhibits(
         &self,
         user_id: &str,
         matter_id: &str,
-        deadline_id: Uuid,
-        params: &OverrideDeadlineParams,
-    ) -> Result<MatterDeadlineRecord, DatabaseError>;
-    /// List the complete override audit trail for a deadline, newest first.
-    async fn list_deadline_override_audit(
+    ) -> Result<Vec<MatterExhibitRecord>, DatabaseError>;
+    /// Withdraw a marked exhibit, stamping `withdrawn_at`. Returns `None` if
+    /// the exhibit does not exist; withdrawing twice is a no-op.
+    async fn withdraw_matter_exhibit(
         &self,
         user_id: &str,
         matter_id: &str,
-        deadline_id: Uuid,
-    ) -> Result<Vec<DeadlineOverrideAuditRecord>, DatabaseError>;
+        exhibit_id: Uuid,
+    ) -> Result<Option<MatterExhibitRecord>, DatabaseError>;
+}
+
+/// Whether a settlement proposal asks for money (`Demand`) or offers it
+/// (`Offer`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettlementProposalKind {
+    Demand,
+    Offer,
+}
+
+impl SettlementProposalKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Demand => "demand",
+            Self::Offer => "offer",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "demand" => Some(Self::Demand),
+            "offer" => Some(Self::Offer),
+            _ => None,
+        }
+    }
+}
+
+/// Which side of the negotiation made a settlement proposal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettlementSide {
+    /// Made by us on the client's behalf; checked against authority.
+    Client,
+    Opposing,
+}
+
+impl SettlementSide {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Client => "client",
+            Self::Opposing => "opposing",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "client" => Some(Self::Client),
+            "opposing" => Some(Self::Opposing),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettlementProposalStatus {
+    Open,
+    Accepted,
+    Rejected,
+    Countered,
+    Withdrawn,
+    Expired,
+}
+
+impl SettlementProposalStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Accepted => "accepted",
+            Self::Rejected => "rejected",
+            Self::Countered => "countered",
+            Self::Withdrawn => "withdrawn",
+            Self::Expired => "expired",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "open" => Some(Self::Open),
+            "accepted" => Some(Self::Accepted),
+            "rejected" => Some(Self::Rejected),
+            "countered" => Some(Self::Countered),
+            "withdrawn" => Some(Self::Withdrawn),
+            "expired" => Some(Self::Expired),
+            _ => None,
+        }
+    }
+}
+
+/// One demand or offer exchanged on a matter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettlementProposalRecord {
+    pub id: Uuid,
+    pub user_id: String,
+    pub matter_id: String,
+    pub kind: SettlementProposalKind,
+    pub side: SettlementSide,
+    pub from_party: String,
+    pub to_party: Option<String>,
+    /// `None` for proposals with non-monetary terms only.
+    pub amount: Option<Decimal>,
+    pub terms: Option<String>,
+    pub proposed_on: NaiveDate,
+    pub expires_on: Option<NaiveDate>,
+    pub status: SettlementProposalStatus,
+    pub response_notes: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateSettlementProposalParams {
+    pub kind: SettlementProposalKind,
+    pub side: SettlementSide,
+    pub from_party: String,
+    pub to_party: Option<String>,
+    pub amount: Option<Decimal>,
+    pub terms: Option<String>,
+    pub proposed_on: NaiveDate,
+    pub expires_on: Option<NaiveDate>,
+    pub created_by: String,
+}
+
+/// Direction of a client's settlement authority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettlementAuthorityKind {
+    /// Lowest amount the client will accept (client is receiving).
+    Minimum,
+    /// Highest amount the client will pay (client is paying).
+    Maximum,
+}
+
+impl SettlementAuthorityKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Minimum => "minimum",
+            Self::Maximum => "maximum",
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "minimum" => Some(Self::Minimum),
+            "maximum" => Some(Self::Maximum),
+            _ => None,
+        }
+    }
+}
+
+/// A grant of settlement authority from the client. Grants are never
+/// edited; a new grant supersedes the previous one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettlementAuthorityRecord {
+    pub id: Uuid,
+    pub user_id: String,
+    pub matter_id: String,
+    pub kind: SettlementAuthorityKind,
+    pub amount: Decimal,
+    pub granted_on: NaiveDate,
+    /// Client contact who gave the authority.
+    pub granted_by: Option<String>,
+    pub notes: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateSettlementAuthorityParams {
+    pub kind: SettlementAuthorityKind,
+    pub amount: Decimal,
+    pub granted_on: NaiveDate,
+    pub granted_by: Option<String>,
+    pub notes: Option<String>,
+    pub created_by: String,
 }
 
 #[async_trait]
-pub trait MatterDocumentStore: Send + Sync {
-    async fn list_matter_documents_db(
+pub trait MatterSettlementStore: Send + Sync {
+    async fn create_settlement_proposal(
         &self,
         user_id: &str,
         matter_id: &str,
-    ) -> Result<Vec<MatterDocumentRecord>, DatabaseError>;
-    async fn get_matter_document(
+        input: &CreateSettlementProposalParams,
+    ) -> Result<SettlementProposalRecord, DatabaseError>;
+    /// Proposals for a matter in negotiation order (date, then entry order).
+    async fn list_settlement_proposals(
         &self,
         user_id: &str,
         matter_id: &str,
-        matter_document_id: Uuid,
-    ) -> Result<Option<MatterDocumentRecord>, DatabaseError>;
-    async fn get_matter_document_by_id(
+    ) -> Result<Vec<SettlementProposalRecord>, DatabaseError>;
+    /// Set a proposal's status. `response_notes` replaces the stored notes
+    /// when given. Returns `None` if the proposal does not exist.
+    async fn update_settlement_proposal_status(
         &self,
         user_id: &str,
-        matter_document_id: Uuid,
-    ) -> Result<Option<MatterDocumentRecord>, DatabaseError>;
-    async fn upsert_matter_document(
+        matter_id: &str,
+        proposal_id: Uuid,
+        status: SettlementProposalStatus,
+        response_notes: Option<&str>,
+    ) -> Result<Option<SettlementProposalRecord>, DatabaseError>;
+    async fn create_settlement_authority(
         &self,
         user_id: &str,
         matter_id: &str,
-        input: &UpsertMatterDocumentParams,
-    ) -> Result<MatterDocumentRecord, DatabaseError>;
-    async fn update_matter_document(
+        input: &CreateSettlementAuthorityParams,
+    ) -> Result<SettlementAuthorityRecord, DatabaseError>;
+    /// Authority grants for a matter, newest first; the first is in force.
+    async fn list_settlement_authority(
         &self,
         user_id: &str,
         matter_id: &str,
-        matter_document_id: Uuid,
-        input: &UpdateMatterDocumentParams,
-    ) -> Result<Option<MatterDocumentRecord>, DatabaseError>;
-    async fn delete_matter_document(
+    ) -> Result<Vec<SettlementAuthorityRecord>, DatabaseError>;
+}
+
+/// Closed-matter work product promoted into the firm precedent bank. The
+/// precedent file carries no client details; the source matter is kept here
+/// for attribution and ethical-wall checks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrecedentRecord {
+    pub id: Uuid,
+    pub user_id: String,
+    pub title: String,
+    /// Workspace path of the anonymized copy under `precedent/`.
+    pub path: String,
+    pub source_matter_id: String,
+    /// Workspace path of the original document in the source matter.
+    pub source_path: String,
+    pub document_type: Option<String>,
+    pub practice_area: Option<String>,
+    pub jurisdiction: Option<String>,
+    pub promoted_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreatePrecedentParams {
+    pub title: String,
+    pub path: String,
+    pub source_matter_id: String,
+    pub source_path: String,
+    pub document_type: Option<String>,
+    pub practice_area: Option<String>,
+    pub jurisdiction: Option<String>,
+    pub promoted_by: String,
+}
+
+#[async_trait]
+pub trait PrecedentStore: Send + Sync {
+    async fn create_precedent(
         &self,
         user_id: &str,
-        matter_id: &str,
-        matter_document_id: Uuid,
-    ) -> Result<bool, DatabaseError>;
+        input: &CreatePrecedentParams,
+    ) -> Result<PrecedentRecord, DatabaseError>;
+    /// Every precedent in the bank, newest first.
+    async fn list_precedents(&self, user_id: &str) -> Result<Vec<PrecedentRecord>, DatabaseError>;
 }
 
 #[async_trait]
-pub trait CitationVerificationStore: Send + Sync {
-    async fn create_citation_verification_run(
+pub trait ClientStatusReportStore: Send + Sync {
+    /// Queue a drafted report for attorney approval. Re-drafting the same
+    /// report path resets it to `PendingApproval` and clears the review.
+    async fn upsert_client_status_report(
         &self,
         user_id: &str,
-        input: &CreateCitationVerificationRunParams,
-        results: &[CreateCitationVerificationResultParams],
-    ) -> Result<
-        (
-            CitationVerificationRunRecord,
-            Vec<CitationVerificationResultRecord>,
-        ),
-        DatabaseError,
-    >;
-    async fn latest_citation_verification_run(
+        matter_id: &str,
+        input: &UpsertClientStatusReportParams,
+    ) -> Result<ClientStatusReportRecord, DatabaseError>;
+    async fn list_client_status_reports(
         &self,
         user_id: &str,
-        matter_document_id: Uuid,
-    ) -> Result<Option<CitationVerificationRunRecord>, DatabaseError>;
-    async fn list_citation_verification_results(
+        matter_id: &str,
+    ) -> Result<Vec<ClientStatusReportRecord>, DatabaseError>;
+    async fn get_client_status_report(
         &self,
         user_id: &str,
-        matter_document_id: Uuid,
-    ) -> Result<Vec<CitationVerificationResultRecord>, DatabaseError>;
-    async fn set_matter_document_readiness(
+        matter_id: &str,
+        report_id: Uuid,
+    ) -> Result<Option<ClientStatusReportRecord>, DatabaseError>;
+    /// Record a review or delivery outcome. `reviewed_at` is stamped on
+    /// approval or rejection and `sent_at` when the status becomes `Sent`;
+    /// `delivery_error` is replaced on every update.
+    async fn update_client_status_report(
         &self,
         user_id: &str,
         matter_id: &str,
-        matter_document_id: Uuid,
-        state: DocumentReadinessState,
-    ) -> Result<Option<MatterDocumentRecord>, DatabaseError>;
+        report_id: Uuid,
+        input: &UpdateClientStatusReportParams,
+    ) -> Result<Option<ClientStatusReportRecord>, DatabaseError>;
 }
 
 #[async_trait]
@@ -2280,6 +4164,12 @@ pub trait DocumentVersionStore: Send + Sync {
         user_id: &str,
         input: &CreateDocumentVersionParams,
     ) -> Result<DocumentVersionRecord, DatabaseError>;
+    /// Count versions across every document registered to a matter.
+    async fn count_matter_document_versions(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<u64, DatabaseError>;
 }
 
 #[async_trait]
@@ -2318,6 +4208,48 @@ pub trait DocumentTemplateStore: Send + Sync {
     ) -> Result<bool, DatabaseError>;
 }
 
+#[async_trait]
+pub trait TemplateLibraryStore: Send + Sync {
+    /// Create or replace the library metadata of a shared template.
+    async fn set_template_library_entry(
+        &self,
+        user_id: &str,
+        template_id: Uuid,
+        input: &SetTemplateLibraryEntryParams,
+    ) -> Result<TemplateLibraryEntryRecord, DatabaseError>;
+    async fn list_template_library_entries(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<TemplateLibraryEntryRecord>, DatabaseError>;
+    /// Snapshot the template's current name, body, and variables as the next
+    /// version. Returns `None` when the template is missing or unchanged
+    /// since its latest version.
+    async fn record_document_template_version(
+        &self,
+        user_id: &str,
+        template_id: Uuid,
+        created_by: &str,
+    ) -> Result<Option<DocumentTemplateVersionRecord>, DatabaseError>;
+    /// Versions of one template, newest first.
+    async fn list_document_template_versions(
+        &self,
+        user_id: &str,
+        template_id: Uuid,
+    ) -> Result<Vec<DocumentTemplateVersionRecord>, DatabaseError>;
+    async fn record_document_template_use(
+        &self,
+        user_id: &str,
+        input: &RecordDocumentTemplateUseParams,
+    ) -> Result<(), DatabaseError>;
+    /// Usage per template since `since` (all time when `None`), most used
+    /// first.
+    async fn document_template_usage(
+        &self,
+        user_id: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<DocumentTemplateUsageRecord>, DatabaseError>;
+}
+
 #[async_trait]
 pub trait TimeExpenseStore: Send + Sync {
     async fn list_time_entries(
@@ -2568,6 +4500,82 @@ pub trait AuditEventStore: Send + Sync {
     ) -> Result<usize, DatabaseError>;
 }
 
+/// A user's rows that backend migration does not copy: history, plus the
+/// practice tables it has no copy path for.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct HistoryCounts {
+    pub conversations: usize,
+    pub conversation_messages: usize,
+    pub jobs: usize,
+    pub routine_runs: usize,
+    pub cost_entries: usize,
+    pub document_revisions: usize,
+    pub trashed_documents: usize,
+    pub party_candidates: usize,
+    pub translations: usize,
+    pub status_reports: usize,
+    pub spend_caps: usize,
+    pub sms_consents: usize,
+    pub matter_facts: usize,
+    pub document_entities: usize,
+    pub document_tags: usize,
+    pub library_templates: usize,
+    pub template_versions: usize,
+    pub template_uses: usize,
+    pub job_artifacts: usize,
+    pub notifications: usize,
+    pub org_memberships: usize,
+    pub share_links: usize,
+    pub watchlist_parties: usize,
+    pub exhibits: usize,
+    pub settlement_proposals: usize,
+    pub settlement_authority: usize,
+    pub comm_preferences: usize,
+    pub comm_consents: usize,
+    pub precedents: usize,
+}
+
+impl HistoryCounts {
+    /// `(name, count)` pairs.
+    pub fn rows(&self) -> [(&'static str, usize); 29] {
+        [
+            ("conversations", self.conversations),
+            ("conversation_messages", self.conversation_messages),
+            ("jobs", self.jobs),
+            ("routine_runs", self.routine_runs),
+            ("cost_entries", self.cost_entries),
+            ("document_revisions", self.document_revisions),
+            ("trashed_documents", self.trashed_documents),
+            ("party_candidates", self.party_candidates),
+            ("translations", self.translations),
+            ("status_reports", self.status_reports),
+            ("spend_caps", self.spend_caps),
+            ("sms_consents", self.sms_consents),
+            ("matter_facts", self.matter_facts),
+            ("document_entities", self.document_entities),
+            ("document_tags", self.document_tags),
+            ("library_templates", self.library_templates),
+            ("template_versions", self.template_versions),
+            ("template_uses", self.template_uses),
+            ("job_artifacts", self.job_artifacts),
+            ("notifications", self.notifications),
+            ("org_memberships", self.org_memberships),
+            ("share_links", self.share_links),
+            ("watchlist_parties", self.watchlist_parties),
+            ("exhibits", self.exhibits),
+            ("settlement_proposals", self.settlement_proposals),
+            ("settlement_authority", self.settlement_authority),
+            ("comm_preferences", self.comm_preferences),
+            ("comm_consents", self.comm_consents),
+            ("precedents", self.precedents),
+        ]
+    }
+
+    pub fn total(&self) -> usize {
+        self.rows().iter().map(|(_, count)| count).sum()
+    }
+}
+
 #[async_trait]
 pub trait LegalRestoreStore: Send + Sync {
     async fn upsert_matter_task_record(&self, row: &MatterTaskRecord) -> Result<(), DatabaseError>;
@@ -2599,6 +4607,8 @@ pub trait LegalRestoreStore: Send + Sync {
         row: &TrustLedgerEntryRecord,
     ) -> Result<(), DatabaseError>;
     async fn upsert_audit_event_record(&self, row: &AuditEventRecord) -> Result<(), DatabaseError>;
+    /// Count `user_id`'s rows that backend migration leaves behind.
+    async fn count_history_rows(&self, user_id: &str) -> Result<HistoryCounts, DatabaseError>;
 }
 
 #[async_trait]
@@ -2648,13 +4658,65 @@ pub trait WorkspaceStore: Send + Sync {
         agent_id: Option<Uuid>,
         path: &str,
     ) -> Result<MemoryDocument, WorkspaceError>;
-    async fn update_document(&self, id: Uuid, content: &str) -> Result<(), WorkspaceError>;
+    /// Replace a document's content. With `if_match`, the write only goes
+    /// through while the stored content still hashes to that ETag (`*`
+    /// matches anything); otherwise it fails with `WriteConflict`.
+    async fn update_document(
+        &self,
+        id: Uuid,
+        content: &str,
+        if_match: Option<&str>,
+    ) -> Result<(), WorkspaceError>;
     async fn delete_document_by_path(
         &self,
         user_id: &str,
         agent_id: Option<Uuid>,
         path: &str,
     ) -> Result<(), WorkspaceError>;
+    /// Move a document and drop its chunks into the trash in one step.
+    async fn trash_document_by_path(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        path: &str,
+        deleted_by: &str,
+    ) -> Result<TrashedDocument, WorkspaceError>;
+    /// Trash entries, most recently deleted first.
+    async fn list_trashed_documents(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+    ) -> Result<Vec<TrashedDocument>, WorkspaceError>;
+    async fn get_trashed_document(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        id: Uuid,
+    ) -> Result<Option<TrashedDocument>, WorkspaceError>;
+    /// Drop one trash entry; `false` when it was not in the trash.
+    async fn delete_trashed_document(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        id: Uuid,
+    ) -> Result<bool, WorkspaceError>;
+    /// Drop trash entries deleted before `cutoff`, returning what was purged.
+    async fn purge_trashed_documents(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<TrashedDocument>, WorkspaceError>;
+    /// Content revisions of a document, newest first.
+    async fn list_document_revisions(
+        &self,
+        document_id: Uuid,
+    ) -> Result<Vec<DocumentRevision>, WorkspaceError>;
+    async fn get_document_revision(
+        &self,
+        document_id: Uuid,
+        revision: i64,
+    ) -> Result<Option<DocumentRevision>, WorkspaceError>;
     async fn list_directory(
         &self,
         user_id: &str,
@@ -2690,6 +4752,13 @@ pub trait WorkspaceStore: Send + Sync {
         agent_id: Option<Uuid>,
         limit: usize,
     ) -> Result<Vec<MemoryChunk>, WorkspaceError>;
+    /// Look up a chunk by ID, scoped to the owning user and agent.
+    async fn get_chunk(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        chunk_id: Uuid,
+    ) -> Result<Option<CitedChunk>, WorkspaceError>;
     async fn hybrid_search(
         &self,
         user_id: &str,
@@ -2698,6 +4767,26 @@ pub trait WorkspaceStore: Send + Sync {
         embedding: Option<&[f32]>,
         config: &SearchConfig,
     ) -> Result<Vec<SearchResult>, WorkspaceError>;
+    /// Aggregate document/chunk storage for paths starting with `path_prefix`
+    /// (all documents when `None`).
+    async fn storage_usage(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        path_prefix: Option<&str>,
+    ) -> Result<WorkspaceStorageUsage, WorkspaceError>;
+}
+
+/// Connection pool occupancy, for backends that pool connections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+    pub max_size: usize,
+    /// Connections currently open.
+    pub size: usize,
+    /// Open connections not checked out.
+    pub available: usize,
+    /// Callers waiting for a connection.
+    pub waiting: usize,
 }
 
 /// Backend-agnostic database supertrait.
@@ -2711,8 +4800,19 @@ pub trait Database:
     + SandboxStore
     + RoutineStore
     + ToolFailureStore
+    + LlmResponseCacheStore
+    + CostLedgerStore
+    + NotificationStore
+    + DocumentShareStore
+    + SmsConsentStore
+    + ClientCommunicationStore
+    + FactStore
+    + DocumentEntityStore
+    + DocumentTagStore
     + LegalConflictStore
+    + PartyWatchlistStore
     + RbacStore
+    + OrganizationStore
     + ClientStore
     + MatterStore
     + MatterTaskStore
@@ -2720,8 +4820,14 @@ pub trait Database:
     + MatterDeadlineStore
     + MatterDocumentStore
     + CitationVerificationStore
+    + DocumentTranslationStore
+    + MatterExhibitStore
+    + MatterSettlementStore
+    + PrecedentStore
+    + ClientStatusReportStore
     + DocumentVersionStore
     + DocumentTemplateStore
+    + TemplateLibraryStore
     + TimeExpenseStore
     + BillingRateStore
     + BillingStore
@@ -2735,4 +4841,9 @@ pub trait Database:
 {
     /// Run schema migrations for this backend.
     async fn run_migrations(&self) -> Result<(), DatabaseError>;
+
+    /// Connection pool occupancy; `None` for backends without a pool.
+    fn pool_stats(&self) -> Option<PoolStats> {
+        None
+    }
 }
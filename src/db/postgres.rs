@@ -3,7 +3,23 @@
 //! Delegates to the existing `Store` (history) and `Repository` (workspace)
 //! implementations, avoiding SQL duplication.
 
+mod client_communications;
+mod cost_ledger;
+mod document_entities;
+mod document_shares;
+mod document_tags;
+mod facts;
+pub mod failover;
 mod legal_hardening;
+mod llm_cache;
+mod matter_exhibits;
+mod matter_settlement;
+mod notifications;
+mod organizations;
+mod party_watchlist;
+mod precedents;
+mod sms_consent;
+mod template_library;
 
 use std::collections::{HashMap, HashSet};
 
@@ -26,13 +42,15 @@ use crate::db::{
     CreateInvoiceParams, CreateMatterDeadlineParams, CreateMatterNoteParams,
     CreateMatterTaskParams, CreateTimeEntryParams, CreateTrustLedgerEntryParams, Database,
     DeadlineOverrideAuditRecord, DocumentTemplateRecord, DocumentTemplateStore,
-    DocumentVersionRecord, DocumentVersionStore, ExpenseCategory, ExpenseEntryRecord,
-    InvoiceLineItemRecord, InvoiceRecord, InvoiceStatus, JobStore, LegalConflictStore,
-    MatterDeadlineRecord, MatterDeadlineStore, MatterDeadlineType, MatterDocumentCategory,
-    MatterDocumentRecord, MatterDocumentStore, MatterMemberRole, MatterMembershipRecord,
-    MatterNoteRecord, MatterNoteStore, MatterRecord, MatterStatus, MatterStore, MatterTaskRecord,
-    MatterTaskStatus, MatterTaskStore, MatterTimeSummary, OverrideDeadlineParams, PartyRole,
-    RbacStore, RecordInvoicePaymentParams, RecordInvoicePaymentResult, RoutineStore, SandboxStore,
+    DocumentVersionRecord, DocumentVersionStore, EstimationSampleRecord, ExpenseCategory,
+    ExpenseEntryRecord, InvoiceLineItemRecord, InvoiceRecord, InvoiceStatus, JobArtifactRecord,
+    JobStore, LegalConflictStore, MatterDeadlineRecord, MatterDeadlineStore, MatterDeadlineType,
+    MatterDocumentCategory, MatterDocumentRecord, MatterDocumentStore, MatterMemberRole,
+    MatterMembershipRecord, MatterNoteRecord, MatterNoteStore, MatterRecord, MatterStatus,
+    MatterStore, MatterTaskChecklistItem, MatterTaskMove, MatterTaskRecord, MatterTaskStatus,
+    MatterTaskStore, MatterTimeSummary, OrgRole, OverrideDeadlineParams, PartyCandidateRecord,
+    PartyCandidateStatus, PartyEntityType, PartyRole, PoolStats, RbacStore,
+    RecordInvoicePaymentParams, RecordInvoicePaymentResult, RoutineStore, SandboxStore,
     SettingsStore, TimeEntryRecord, TimeExpenseStore, ToolFailureStore, TrustAccountingStore,
     TrustLedgerEntryRecord, TrustLedgerEntryType, UpdateClientParams, UpdateDocumentTemplateParams,
     UpdateExpenseEntryParams, UpdateMatterDeadlineParams, UpdateMatterDocumentParams,
@@ -47,7 +65,8 @@ use crate::history::{
     SandboxJobSummary, SettingRow, Store,
 };
 use crate::workspace::{
-    MemoryChunk, MemoryDocument, Repository, SearchConfig, SearchResult, WorkspaceEntry,
+    CitedChunk, DocumentRevision, MemoryChunk, MemoryDocument, Repository, SearchConfig,
+    SearchResult, TrashedDocument, WorkspaceEntry, WorkspaceStorageUsage,
 };
 
 /// PostgreSQL database backend.
@@ -64,7 +83,7 @@ impl PgBackend {
     /// Create a new PostgreSQL backend from configuration.
     pub async fn new(config: &DatabaseConfig) -> Result<Self, DatabaseError> {
         let store = Store::new(config).await?;
-        let repo = Repository::new(store.pool());
+        let repo = Repository::with_pools(store.pools().clone());
         Ok(Self { store, repo })
     }
 
@@ -292,6 +311,38 @@ where
     })
 }
 
+const PARTY_CANDIDATE_COLUMNS: &str = "id, matter_id, name, entity_type, source_path, snippet, \
+     confidence, extractor, status, party_id, reviewed_by, reviewed_at, created_at, updated_at";
+
+fn row_to_party_candidate_record(
+    row: &tokio_postgres::Row,
+) -> Result<PartyCandidateRecord, DatabaseError> {
+    let entity_type_raw: String = row.get("entity_type");
+    let entity_type = PartyEntityType::from_db_value(&entity_type_raw).ok_or_else(|| {
+        DatabaseError::Serialization(format!("invalid entity type '{}'", entity_type_raw))
+    })?;
+    let status_raw: String = row.get("status");
+    let status = PartyCandidateStatus::from_db_value(&status_raw).ok_or_else(|| {
+        DatabaseError::Serialization(format!("invalid party candidate status '{}'", status_raw))
+    })?;
+    Ok(PartyCandidateRecord {
+        id: row.get("id"),
+        matter_id: row.get("matter_id"),
+        name: row.get("name"),
+        entity_type,
+        source_path: row.get("source_path"),
+        snippet: row.get("snippet"),
+        confidence: row.get("confidence"),
+        extractor: row.get("extractor"),
+        status,
+        party_id: row.get("party_id"),
+        reviewed_by: row.get("reviewed_by"),
+        reviewed_at: row.get("reviewed_at"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
 fn dedupe_hits(rows: Vec<(ConflictHit, f64)>, limit: usize) -> Vec<ConflictHit> {
     let mut best: std::collections::HashMap<(String, String, String), (u8, f64, ConflictHit)> =
         std::collections::HashMap::new();
@@ -446,12 +497,59 @@ fn row_to_matter_record(row: &tokio_postgres::Row) -> Result<MatterRecord, Datab
     })
 }
 
+const MATTER_TASK_COLUMNS: &str = "id, user_id, matter_id, title, description, status, assignee, due_at, \
+     blocked_by, position, parent_task_id, checklist, created_at, updated_at";
+
+fn task_checklist_json(
+    items: &[MatterTaskChecklistItem],
+) -> Result<serde_json::Value, DatabaseError> {
+    serde_json::to_value(items).map_err(|e| DatabaseError::Serialization(e.to_string()))
+}
+
+/// Mark `parent_id` done once all of its subtasks are done or cancelled (at
+/// least one done), then repeat for its own parent.
+async fn roll_up_matter_task_parents_pg<C>(
+    conn: &C,
+    user_id: &str,
+    matter_id: &str,
+    mut parent_id: Option<Uuid>,
+) -> Result<(), DatabaseError>
+where
+    C: GenericClient + Sync,
+{
+    let mut seen = HashSet::new();
+    while let Some(id) = parent_id.take().filter(|id| seen.insert(*id)) {
+        let completed = conn
+            .query_opt(
+                "UPDATE matter_tasks SET \
+                    status = 'done', \
+                    position = (SELECT COALESCE(MAX(position), -1) + 1 FROM matter_tasks \
+                                WHERE user_id = $1 AND matter_id = $2 AND status = 'done'), \
+                    updated_at = NOW() \
+                 WHERE user_id = $1 AND matter_id = $2 AND id = $3 \
+                   AND status NOT IN ('done', 'cancelled') \
+                   AND EXISTS (SELECT 1 FROM matter_tasks \
+                               WHERE user_id = $1 AND matter_id = $2 AND parent_task_id = $3 \
+                                 AND status = 'done') \
+                   AND NOT EXISTS (SELECT 1 FROM matter_tasks \
+                                   WHERE user_id = $1 AND matter_id = $2 AND parent_task_id = $3 \
+                                     AND status NOT IN ('done', 'cancelled')) \
+                 RETURNING parent_task_id",
+                &[&user_id, &matter_id, &id],
+            )
+            .await?;
+        parent_id = completed.and_then(|row| row.get("parent_task_id"));
+    }
+    Ok(())
+}
+
 fn row_to_matter_task_record(row: &tokio_postgres::Row) -> Result<MatterTaskRecord, DatabaseError> {
     let status_raw: String = row.get("status");
     let status = MatterTaskStatus::from_db_value(&status_raw).ok_or_else(|| {
         DatabaseError::Serialization(format!("invalid matter task status '{}'", status_raw))
     })?;
     let blocked_by_value: serde_json::Value = row.get("blocked_by");
+    let checklist_value: serde_json::Value = row.get("checklist");
     Ok(MatterTaskRecord {
         id: row.get("id"),
         user_id: row.get("user_id"),
@@ -462,6 +560,10 @@ fn row_to_matter_task_record(row: &tokio_postgres::Row) -> Result<MatterTaskReco
         assignee: row.get("assignee"),
         due_at: row.get("due_at"),
         blocked_by: parse_json_uuid_array(&blocked_by_value),
+        position: row.get("position"),
+        parent_task_id: row.get("parent_task_id"),
+        checklist: serde_json::from_value(checklist_value)
+            .map_err(|e| DatabaseError::Serialization(e.to_string()))?,
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
     })
@@ -475,6 +577,7 @@ fn row_to_matter_note_record(row: &tokio_postgres::Row) -> MatterNoteRecord {
         author: row.get("author"),
         body: row.get("body"),
         pinned: row.get("pinned"),
+        parent_id: row.get("parent_id"),
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
     }
@@ -772,6 +875,16 @@ impl Database for PgBackend {
     async fn run_migrations(&self) -> Result<(), DatabaseError> {
         self.store.run_migrations().await
     }
+
+    fn pool_stats(&self) -> Option<PoolStats> {
+        let status = self.pool().status();
+        Some(PoolStats {
+            max_size: status.max_size,
+            size: status.size,
+            available: status.available,
+            waiting: status.waiting,
+        })
+    }
 }
 
 // ==================== ConversationStore ====================
@@ -1003,6 +1116,14 @@ impl JobStore for PgBackend {
             .update_estimation_actuals(id, actual_cost, actual_time_secs, actual_value)
             .await
     }
+
+    async fn list_estimation_samples(
+        &self,
+        category: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<EstimationSampleRecord>, DatabaseError> {
+        self.store.list_estimation_samples(category, limit).await
+    }
 }
 
 // ==================== SandboxStore ====================
@@ -1075,6 +1196,18 @@ impl SandboxStore for PgBackend {
         self.store.get_sandbox_job_mode(id).await
     }
 
+    async fn update_sandbox_job_priority(
+        &self,
+        id: Uuid,
+        priority: &str,
+    ) -> Result<(), DatabaseError> {
+        self.store.update_sandbox_job_priority(id, priority).await
+    }
+
+    async fn get_sandbox_job_priority(&self, id: Uuid) -> Result<Option<String>, DatabaseError> {
+        self.store.get_sandbox_job_priority(id).await
+    }
+
     async fn save_job_event(
         &self,
         job_id: Uuid,
@@ -1091,6 +1224,54 @@ impl SandboxStore for PgBackend {
     ) -> Result<Vec<JobEventRecord>, DatabaseError> {
         self.store.list_job_events(job_id, limit).await
     }
+
+    async fn list_job_events_after(
+        &self,
+        job_id: Uuid,
+        after_id: i64,
+        limit: i64,
+    ) -> Result<Vec<JobEventRecord>, DatabaseError> {
+        self.store
+            .list_job_events_after(job_id, after_id, limit)
+            .await
+    }
+
+    async fn save_job_artifact(&self, artifact: &JobArtifactRecord) -> Result<(), DatabaseError> {
+        self.store.save_job_artifact(artifact).await
+    }
+
+    async fn list_job_artifacts(
+        &self,
+        job_id: Uuid,
+    ) -> Result<Vec<JobArtifactRecord>, DatabaseError> {
+        self.store.list_job_artifacts(job_id).await
+    }
+
+    async fn get_job_artifact(
+        &self,
+        job_id: Uuid,
+        name: &str,
+    ) -> Result<Option<JobArtifactRecord>, DatabaseError> {
+        self.store.get_job_artifact(job_id, name).await
+    }
+
+    async fn mark_job_artifact_promoted(
+        &self,
+        job_id: Uuid,
+        name: &str,
+        promoted_to: &str,
+    ) -> Result<(), DatabaseError> {
+        self.store
+            .mark_job_artifact_promoted(job_id, name, promoted_to)
+            .await
+    }
+
+    async fn delete_job_artifacts_before(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<JobArtifactRecord>, DatabaseError> {
+        self.store.delete_job_artifacts_before(cutoff).await
+    }
 }
 
 // ==================== RoutineStore ====================
@@ -1873,6 +2054,119 @@ impl LegalConflictStore for PgBackend {
         tx.commit().await?;
         party_relationship_record_pg(&conn, relationship_id).await
     }
+
+    async fn upsert_party_candidate(
+        &self,
+        matter_id: &str,
+        input: &crate::db::UpsertPartyCandidateParams,
+    ) -> Result<PartyCandidateRecord, DatabaseError> {
+        let name = input.name.trim();
+        let normalized = normalize_party_name(name);
+        if normalized.is_empty() {
+            return Err(DatabaseError::Serialization(
+                "party candidate name cannot be empty".to_string(),
+            ));
+        }
+        let conn = self.store.conn().await?;
+        conn.execute(
+            "INSERT INTO party_candidates \
+             (id, matter_id, name, name_normalized, entity_type, source_path, snippet, confidence, extractor) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) \
+             ON CONFLICT (matter_id, name_normalized, source_path) DO UPDATE SET \
+                snippet = COALESCE(EXCLUDED.snippet, party_candidates.snippet), \
+                confidence = GREATEST(EXCLUDED.confidence, party_candidates.confidence), \
+                updated_at = NOW() \
+             WHERE party_candidates.status = 'pending'",
+            &[
+                &Uuid::new_v4(),
+                &matter_id,
+                &name,
+                &normalized,
+                &input.entity_type.as_str(),
+                &input.source_path,
+                &input.snippet,
+                &input.confidence,
+                &input.extractor,
+            ],
+        )
+        .await?;
+        let row = conn
+            .query_one(
+                &format!(
+                    "SELECT {PARTY_CANDIDATE_COLUMNS} FROM party_candidates \
+                     WHERE matter_id = $1 AND name_normalized = $2 AND source_path = $3"
+                ),
+                &[&matter_id, &normalized, &input.source_path],
+            )
+            .await?;
+        row_to_party_candidate_record(&row)
+    }
+
+    async fn list_party_candidates(
+        &self,
+        matter_id: &str,
+        status: Option<PartyCandidateStatus>,
+    ) -> Result<Vec<PartyCandidateRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let status = status.map(PartyCandidateStatus::as_str);
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {PARTY_CANDIDATE_COLUMNS} FROM party_candidates \
+                     WHERE matter_id = $1 AND ($2::TEXT IS NULL OR status = $2) \
+                     ORDER BY created_at DESC, name_normalized ASC"
+                ),
+                &[&matter_id, &status],
+            )
+            .await?;
+        rows.iter().map(row_to_party_candidate_record).collect()
+    }
+
+    async fn get_party_candidate(
+        &self,
+        matter_id: &str,
+        candidate_id: Uuid,
+    ) -> Result<Option<PartyCandidateRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "SELECT {PARTY_CANDIDATE_COLUMNS} FROM party_candidates \
+                     WHERE matter_id = $1 AND id = $2"
+                ),
+                &[&matter_id, &candidate_id],
+            )
+            .await?;
+        row.as_ref().map(row_to_party_candidate_record).transpose()
+    }
+
+    async fn review_party_candidate(
+        &self,
+        matter_id: &str,
+        candidate_id: Uuid,
+        input: &crate::db::ReviewPartyCandidateParams,
+    ) -> Result<Option<PartyCandidateRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "UPDATE party_candidates SET \
+                        status = $3, party_id = $4, reviewed_by = $5, \
+                        reviewed_at = NOW(), updated_at = NOW() \
+                     WHERE matter_id = $1 AND id = $2 AND status = 'pending' \
+                     RETURNING {PARTY_CANDIDATE_COLUMNS}"
+                ),
+                &[
+                    &matter_id,
+                    &candidate_id,
+                    &input.status.as_str(),
+                    &input.party_id,
+                    &input.reviewed_by,
+                ],
+            )
+            .await?;
+        row.as_ref().map(row_to_party_candidate_record).transpose()
+    }
 }
 
 #[async_trait]
@@ -2007,14 +2301,43 @@ impl RbacStore for PgBackend {
                 &[&matter_owner_user_id, &matter_id, &requesting_user_id],
             )
             .await?;
-        let Some(row) = row else {
-            return Ok(None);
+        let member_role = match row {
+            Some(row) => {
+                let role_raw: String = row.get("role");
+                Some(MatterMemberRole::from_db_value(&role_raw).ok_or_else(|| {
+                    DatabaseError::Serialization(format!(
+                        "invalid matter member role '{}'",
+                        role_raw
+                    ))
+                })?)
+            }
+            None => None,
         };
-        let role_raw: String = row.get("role");
-        let role = MatterMemberRole::from_db_value(&role_raw).ok_or_else(|| {
-            DatabaseError::Serialization(format!("invalid matter member role '{}'", role_raw))
-        })?;
-        Ok(Some(role))
+        let org_row = conn
+            .query_opt(
+                "SELECT om.role FROM matters m \
+                 JOIN organization_members om ON om.org_id = m.org_id \
+                 WHERE m.user_id = $1 AND m.matter_id = $2 AND om.user_id = $3",
+                &[&matter_owner_user_id, &matter_id, &requesting_user_id],
+            )
+            .await?;
+        let org_role = match org_row {
+            Some(org_row) => {
+                let org_role_raw: String = org_row.get("role");
+                let org_role = OrgRole::from_db_value(&org_role_raw).ok_or_else(|| {
+                    DatabaseError::Serialization(format!(
+                        "invalid organization role '{}'",
+                        org_role_raw
+                    ))
+                })?;
+                Some(org_role.matter_role())
+            }
+            None => None,
+        };
+        Ok(match (member_role, org_role) {
+            (Some(member), Some(org)) => Some(member.stronger(org)),
+            (member, org) => member.or(org),
+        })
     }
 
     async fn remove_matter_membership(
@@ -2148,7 +2471,7 @@ impl ClientStore for PgBackend {
         user_id: &str,
         query: Option<&str>,
     ) -> Result<Vec<ClientRecord>, DatabaseError> {
-        let conn = self.store.conn().await?;
+        let conn = self.store.read_conn().await?;
         let rows = if let Some(search_raw) = query {
             let search = normalize_party_name(search_raw);
             if search.is_empty() {
@@ -2328,7 +2651,7 @@ impl MatterStore for PgBackend {
     }
 
     async fn list_matters_db(&self, user_id: &str) -> Result<Vec<MatterRecord>, DatabaseError> {
-        let conn = self.store.conn().await?;
+        let conn = self.store.read_conn().await?;
         let rows = conn
             .query(
                 "SELECT user_id, matter_id, client_id, status, stage, practice_area, jurisdiction, opened_at, closed_at, assigned_to, custom_fields, created_at, updated_at \
@@ -2417,10 +2740,12 @@ impl MatterTaskStore for PgBackend {
         let conn = self.store.conn().await?;
         let rows = conn
             .query(
-                "SELECT id, user_id, matter_id, title, description, status, assignee, due_at, blocked_by, created_at, updated_at \
-                 FROM matter_tasks \
-                 WHERE user_id = $1 AND matter_id = $2 \
-                 ORDER BY created_at DESC",
+                &format!(
+                    "SELECT {MATTER_TASK_COLUMNS} \
+                     FROM matter_tasks \
+                     WHERE user_id = $1 AND matter_id = $2 \
+                     ORDER BY created_at DESC"
+                ),
                 &[&user_id, &matter_id],
             )
             .await?;
@@ -2443,13 +2768,20 @@ impl MatterTaskStore for PgBackend {
                 .collect::<Vec<_>>(),
         )
         .map_err(|e| DatabaseError::Serialization(e.to_string()))?;
+        let checklist = task_checklist_json(&input.checklist)?;
         let conn = self.store.conn().await?;
         let row = conn
             .query_one(
-                "INSERT INTO matter_tasks \
-                 (id, user_id, matter_id, title, description, status, assignee, due_at, blocked_by) \
-                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) \
-                 RETURNING id, user_id, matter_id, title, description, status, assignee, due_at, blocked_by, created_at, updated_at",
+                &format!(
+                    "INSERT INTO matter_tasks \
+                     (id, user_id, matter_id, title, description, status, assignee, due_at, blocked_by, position, \
+                      parent_task_id, checklist) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, \
+                             (SELECT COALESCE(MAX(position), -1) + 1 FROM matter_tasks \
+                              WHERE user_id = $2 AND matter_id = $3 AND status = $6), \
+                             $10, $11) \
+                     RETURNING {MATTER_TASK_COLUMNS}"
+                ),
                 &[
                     &Uuid::new_v4(),
                     &user_id,
@@ -2460,6 +2792,8 @@ impl MatterTaskStore for PgBackend {
                     &input.assignee,
                     &input.due_at,
                     &blocked_by,
+                    &input.parent_task_id,
+                    &checklist,
                 ],
             )
             .await?;
@@ -2476,9 +2810,11 @@ impl MatterTaskStore for PgBackend {
         let conn = self.store.conn().await?;
         let existing = conn
             .query_opt(
-                "SELECT id, user_id, matter_id, title, description, status, assignee, due_at, blocked_by, created_at, updated_at \
-                 FROM matter_tasks \
-                 WHERE user_id = $1 AND matter_id = $2 AND id = $3",
+                &format!(
+                    "SELECT {MATTER_TASK_COLUMNS} \
+                     FROM matter_tasks \
+                     WHERE user_id = $1 AND matter_id = $2 AND id = $3"
+                ),
                 &[&user_id, &matter_id, &task_id],
             )
             .await?;
@@ -2501,19 +2837,33 @@ impl MatterTaskStore for PgBackend {
         let merged_status = input.status.unwrap_or(existing.status);
         let merged_assignee = input.assignee.clone().unwrap_or(existing.assignee);
         let merged_due_at = input.due_at.unwrap_or(existing.due_at);
+        let merged_parent = input.parent_task_id.unwrap_or(existing.parent_task_id);
+        let checklist = task_checklist_json(
+            input
+                .checklist
+                .as_deref()
+                .unwrap_or(existing.checklist.as_slice()),
+        )?;
 
         let updated = conn
             .query_one(
-                "UPDATE matter_tasks SET \
-                    title = $4, \
-                    description = $5, \
-                    status = $6, \
-                    assignee = $7, \
-                    due_at = $8, \
-                    blocked_by = $9, \
-                    updated_at = NOW() \
-                 WHERE user_id = $1 AND matter_id = $2 AND id = $3 \
-                 RETURNING id, user_id, matter_id, title, description, status, assignee, due_at, blocked_by, created_at, updated_at",
+                &format!(
+                    "UPDATE matter_tasks SET \
+                        title = $4, \
+                        description = $5, \
+                        status = $6, \
+                        assignee = $7, \
+                        due_at = $8, \
+                        blocked_by = $9, \
+                        position = CASE WHEN status = $6 THEN position ELSE \
+                            (SELECT COALESCE(MAX(position), -1) + 1 FROM matter_tasks \
+                             WHERE user_id = $1 AND matter_id = $2 AND status = $6) END, \
+                        parent_task_id = $10, \
+                        checklist = $11, \
+                        updated_at = NOW() \
+                     WHERE user_id = $1 AND matter_id = $2 AND id = $3 \
+                     RETURNING {MATTER_TASK_COLUMNS}"
+                ),
                 &[
                     &user_id,
                     &matter_id,
@@ -2524,9 +2874,16 @@ impl MatterTaskStore for PgBackend {
                     &merged_assignee,
                     &merged_due_at,
                     &blocked_by,
+                    &merged_parent,
+                    &checklist,
                 ],
             )
             .await?;
+        roll_up_matter_task_parents_pg(&conn, user_id, matter_id, merged_parent).await?;
+        if existing.parent_task_id != merged_parent {
+            roll_up_matter_task_parents_pg(&conn, user_id, matter_id, existing.parent_task_id)
+                .await?;
+        }
         Ok(Some(row_to_matter_task_record(&updated)?))
     }
 
@@ -2537,13 +2894,77 @@ impl MatterTaskStore for PgBackend {
         task_id: Uuid,
     ) -> Result<bool, DatabaseError> {
         let conn = self.store.conn().await?;
+        conn.execute(
+            "UPDATE matter_tasks SET parent_task_id = NULL, updated_at = NOW() \
+             WHERE user_id = $1 AND matter_id = $2 AND parent_task_id = $3",
+            &[&user_id, &matter_id, &task_id],
+        )
+        .await?;
         let deleted = conn
-            .execute(
-                "DELETE FROM matter_tasks WHERE user_id = $1 AND matter_id = $2 AND id = $3",
+            .query_opt(
+                "DELETE FROM matter_tasks WHERE user_id = $1 AND matter_id = $2 AND id = $3 \
+                 RETURNING parent_task_id",
                 &[&user_id, &matter_id, &task_id],
             )
             .await?;
-        Ok(deleted > 0)
+        let Some(deleted) = deleted else {
+            return Ok(false);
+        };
+        roll_up_matter_task_parents_pg(&conn, user_id, matter_id, deleted.get("parent_task_id"))
+            .await?;
+        Ok(true)
+    }
+
+    async fn move_matter_tasks(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        moves: &[MatterTaskMove],
+    ) -> Result<bool, DatabaseError> {
+        let mut conn = self.store.conn().await?;
+        let tx = conn.transaction().await?;
+        for step in moves {
+            let (set_assignee, assignee) = match &step.assignee {
+                Some(assignee) => (true, assignee.clone()),
+                None => (false, None),
+            };
+            let updated = tx
+                .execute(
+                    "UPDATE matter_tasks SET \
+                        status = COALESCE($4, status), \
+                        assignee = CASE WHEN $5 THEN $6 ELSE assignee END, \
+                        position = $7, \
+                        updated_at = NOW() \
+                     WHERE user_id = $1 AND matter_id = $2 AND id = $3",
+                    &[
+                        &user_id,
+                        &matter_id,
+                        &step.task_id,
+                        &step.status.map(MatterTaskStatus::as_str),
+                        &set_assignee,
+                        &assignee,
+                        &step.position,
+                    ],
+                )
+                .await?;
+            if updated == 0 {
+                tx.rollback().await?;
+                return Ok(false);
+            }
+        }
+        for step in moves.iter().filter(|step| step.status.is_some()) {
+            let parent_id = tx
+                .query_one(
+                    "SELECT parent_task_id FROM matter_tasks \
+                     WHERE user_id = $1 AND matter_id = $2 AND id = $3",
+                    &[&user_id, &matter_id, &step.task_id],
+                )
+                .await?
+                .get("parent_task_id");
+            roll_up_matter_task_parents_pg(&tx, user_id, matter_id, parent_id).await?;
+        }
+        tx.commit().await?;
+        Ok(true)
     }
 }
 
@@ -2559,7 +2980,7 @@ impl MatterNoteStore for PgBackend {
         let conn = self.store.conn().await?;
         let rows = conn
             .query(
-                "SELECT id, user_id, matter_id, author, body, pinned, created_at, updated_at \
+                "SELECT id, user_id, matter_id, author, body, pinned, parent_id, created_at, updated_at \
                  FROM matter_notes \
                  WHERE user_id = $1 AND matter_id = $2 \
                  ORDER BY pinned DESC, created_at DESC",
@@ -2581,9 +3002,9 @@ impl MatterNoteStore for PgBackend {
         let conn = self.store.conn().await?;
         let row = conn
             .query_one(
-                "INSERT INTO matter_notes (id, user_id, matter_id, author, body, pinned) \
-                 VALUES ($1, $2, $3, $4, $5, $6) \
-                 RETURNING id, user_id, matter_id, author, body, pinned, created_at, updated_at",
+                "INSERT INTO matter_notes (id, user_id, matter_id, author, body, pinned, parent_id) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7) \
+                 RETURNING id, user_id, matter_id, author, body, pinned, parent_id, created_at, updated_at",
                 &[
                     &Uuid::new_v4(),
                     &user_id,
@@ -2591,6 +3012,7 @@ impl MatterNoteStore for PgBackend {
                     &input.author,
                     &input.body,
                     &input.pinned,
+                    &input.parent_id,
                 ],
             )
             .await?;
@@ -2607,7 +3029,7 @@ impl MatterNoteStore for PgBackend {
         let conn = self.store.conn().await?;
         let existing = conn
             .query_opt(
-                "SELECT id, user_id, matter_id, author, body, pinned, created_at, updated_at \
+                "SELECT id, user_id, matter_id, author, body, pinned, parent_id, created_at, updated_at \
                  FROM matter_notes \
                  WHERE user_id = $1 AND matter_id = $2 AND id = $3",
                 &[&user_id, &matter_id, &note_id],
@@ -2630,7 +3052,7 @@ impl MatterNoteStore for PgBackend {
                     pinned = $6, \
                     updated_at = NOW() \
                  WHERE user_id = $1 AND matter_id = $2 AND id = $3 \
-                 RETURNING id, user_id, matter_id, author, body, pinned, created_at, updated_at",
+                 RETURNING id, user_id, matter_id, author, body, pinned, parent_id, created_at, updated_at",
                 &[
                     &user_id,
                     &matter_id,
@@ -2657,6 +3079,14 @@ impl MatterNoteStore for PgBackend {
                 &[&user_id, &matter_id, &note_id],
             )
             .await?;
+        if deleted > 0 {
+            // Replies have no meaning without the note they answer.
+            conn.execute(
+                "DELETE FROM matter_notes WHERE user_id = $1 AND matter_id = $2 AND parent_id = $3",
+                &[&user_id, &matter_id, &note_id],
+            )
+            .await?;
+        }
         Ok(deleted > 0)
     }
 }
@@ -3163,6 +3593,24 @@ impl DocumentVersionStore for PgBackend {
         tx.commit().await?;
         row_to_document_version_record(&inserted)
     }
+
+    async fn count_matter_document_versions(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<u64, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                "SELECT COUNT(*)::bigint \
+                 FROM document_versions v \
+                 JOIN matter_documents md ON md.id = v.matter_document_id \
+                 WHERE v.user_id = $1 AND md.matter_id = $2",
+                &[&user_id, &matter_id],
+            )
+            .await?;
+        Ok(row.get::<_, i64>(0).max(0) as u64)
+    }
 }
 
 // ==================== DocumentTemplateStore ====================
@@ -3386,7 +3834,7 @@ impl TimeExpenseStore for PgBackend {
         user_id: &str,
         matter_id: &str,
     ) -> Result<Vec<TimeEntryRecord>, DatabaseError> {
-        let conn = self.store.conn().await?;
+        let conn = self.store.read_conn().await?;
         let rows = conn
             .query(
                 "SELECT id, user_id, matter_id, timekeeper, description, hours, hourly_rate, entry_date, billable, billed_invoice_id, created_at, updated_at, \
@@ -3552,7 +4000,7 @@ impl TimeExpenseStore for PgBackend {
         user_id: &str,
         matter_id: &str,
     ) -> Result<Vec<ExpenseEntryRecord>, DatabaseError> {
-        let conn = self.store.conn().await?;
+        let conn = self.store.read_conn().await?;
         let rows = conn
             .query(
                 "SELECT id, user_id, matter_id, submitted_by, description, amount, category, entry_date, receipt_path, billable, billed_invoice_id, created_at, updated_at \
@@ -3838,7 +4286,7 @@ impl BillingStore for PgBackend {
         user_id: &str,
         matter_id: Option<&str>,
     ) -> Result<Vec<InvoiceRecord>, DatabaseError> {
-        let conn = self.store.conn().await?;
+        let conn = self.store.read_conn().await?;
         let rows = if let Some(matter_id) = matter_id {
             conn.query(
                 "SELECT id, user_id, matter_id, invoice_number, status, issued_date, due_date, subtotal, tax, total, paid_amount, notes, created_at, updated_at \
@@ -4321,7 +4769,7 @@ impl BillingStore for PgBackend {
         user_id: &str,
         matter_id: &str,
     ) -> Result<Vec<TrustLedgerEntryRecord>, DatabaseError> {
-        let conn = self.store.conn().await?;
+        let conn = self.store.read_conn().await?;
         let rows = conn
             .query(
                 "SELECT id, user_id, matter_id, entry_type, amount, balance_after, description, invoice_id, recorded_by, created_at, \
@@ -4390,7 +4838,7 @@ impl AuditEventStore for PgBackend {
         limit: usize,
         offset: usize,
     ) -> Result<Vec<AuditEventRecord>, DatabaseError> {
-        let conn = self.store.conn().await?;
+        let conn = self.store.read_conn().await?;
         let limit_i64 = i64::try_from(limit)
             .map_err(|_| DatabaseError::Serialization("limit too large".to_string()))?;
         let offset_i64 = i64::try_from(offset)
@@ -4475,8 +4923,9 @@ impl crate::db::LegalRestoreStore for PgBackend {
         let conn = self.store.conn().await?;
         conn.execute(
             "INSERT INTO matter_tasks \
-             (id, user_id, matter_id, title, description, status, assignee, due_at, blocked_by, created_at, updated_at) \
-             VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11) \
+             (id, user_id, matter_id, title, description, status, assignee, due_at, blocked_by, created_at, updated_at, position, \
+              parent_task_id, checklist) \
+             VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14) \
              ON CONFLICT (id) DO UPDATE SET \
                 user_id = EXCLUDED.user_id, \
                 matter_id = EXCLUDED.matter_id, \
@@ -4487,7 +4936,10 @@ impl crate::db::LegalRestoreStore for PgBackend {
                 due_at = EXCLUDED.due_at, \
                 blocked_by = EXCLUDED.blocked_by, \
                 created_at = EXCLUDED.created_at, \
-                updated_at = EXCLUDED.updated_at",
+                updated_at = EXCLUDED.updated_at, \
+                position = EXCLUDED.position, \
+                parent_task_id = EXCLUDED.parent_task_id, \
+                checklist = EXCLUDED.checklist",
             &[
                 &row.id,
                 &row.user_id,
@@ -4500,6 +4952,9 @@ impl crate::db::LegalRestoreStore for PgBackend {
                 &blocked_by,
                 &row.created_at,
                 &row.updated_at,
+                &row.position,
+                &row.parent_task_id,
+                &task_checklist_json(&row.checklist)?,
             ],
         )
         .await?;
@@ -4510,8 +4965,8 @@ impl crate::db::LegalRestoreStore for PgBackend {
         let conn = self.store.conn().await?;
         conn.execute(
             "INSERT INTO matter_notes \
-             (id, user_id, matter_id, author, body, pinned, created_at, updated_at) \
-             VALUES ($1,$2,$3,$4,$5,$6,$7,$8) \
+             (id, user_id, matter_id, author, body, pinned, created_at, updated_at, parent_id) \
+             VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9) \
              ON CONFLICT (id) DO UPDATE SET \
                 user_id = EXCLUDED.user_id, \
                 matter_id = EXCLUDED.matter_id, \
@@ -4519,7 +4974,8 @@ impl crate::db::LegalRestoreStore for PgBackend {
                 body = EXCLUDED.body, \
                 pinned = EXCLUDED.pinned, \
                 created_at = EXCLUDED.created_at, \
-                updated_at = EXCLUDED.updated_at",
+                updated_at = EXCLUDED.updated_at, \
+                parent_id = EXCLUDED.parent_id",
             &[
                 &row.id,
                 &row.user_id,
@@ -4529,6 +4985,7 @@ impl crate::db::LegalRestoreStore for PgBackend {
                 &row.pinned,
                 &row.created_at,
                 &row.updated_at,
+                &row.parent_id,
             ],
         )
         .await?;
@@ -4911,6 +5368,85 @@ impl crate::db::LegalRestoreStore for PgBackend {
         .await?;
         Ok(())
     }
+
+    async fn count_history_rows(
+        &self,
+        user_id: &str,
+    ) -> Result<crate::db::HistoryCounts, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                "SELECT \
+                 (SELECT COUNT(*) FROM conversations WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM conversation_messages m \
+                    JOIN conversations c ON c.id = m.conversation_id WHERE c.user_id = $1), \
+                 (SELECT COUNT(*) FROM agent_jobs WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM routine_runs r \
+                    JOIN routines t ON t.id = r.routine_id WHERE t.user_id = $1), \
+                 (SELECT COUNT(*) FROM cost_entries WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM memory_document_revisions r \
+                    JOIN memory_documents d ON d.id = r.document_id WHERE d.user_id = $1), \
+                 (SELECT COUNT(*) FROM memory_trash WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM party_candidates \
+                    WHERE matter_id IN (SELECT matter_id FROM matters WHERE user_id = $1)), \
+                 (SELECT COUNT(*) FROM document_translations WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM client_status_reports WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM matter_spend_caps WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM sms_consents WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM matter_facts WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM document_entities WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM document_tags WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM document_template_library WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM document_template_versions WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM document_template_uses WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM job_artifacts a \
+                    JOIN agent_jobs j ON j.id = a.job_id WHERE j.user_id = $1), \
+                 (SELECT COUNT(*) FROM notifications WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM organization_members WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM document_share_links WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM party_watchlist WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM matter_exhibits WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM matter_settlement_proposals WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM matter_settlement_authority WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM client_communication_preferences WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM client_communication_consents WHERE user_id = $1), \
+                 (SELECT COUNT(*) FROM precedents WHERE user_id = $1)",
+                &[&user_id],
+            )
+            .await?;
+        let count = |index: usize| row.get::<_, i64>(index).max(0) as usize;
+        Ok(crate::db::HistoryCounts {
+            conversations: count(0),
+            conversation_messages: count(1),
+            jobs: count(2),
+            routine_runs: count(3),
+            cost_entries: count(4),
+            document_revisions: count(5),
+            trashed_documents: count(6),
+            party_candidates: count(7),
+            translations: count(8),
+            status_reports: count(9),
+            spend_caps: count(10),
+            sms_consents: count(11),
+            matter_facts: count(12),
+            document_entities: count(13),
+            document_tags: count(14),
+            library_templates: count(15),
+            template_versions: count(16),
+            template_uses: count(17),
+            job_artifacts: count(18),
+            notifications: count(19),
+            org_memberships: count(20),
+            share_links: count(21),
+            watchlist_parties: count(22),
+            exhibits: count(23),
+            settlement_proposals: count(24),
+            settlement_authority: count(25),
+            comm_preferences: count(26),
+            comm_consents: count(27),
+            precedents: count(28),
+        })
+    }
 }
 
 // ==================== SettingsStore ====================
@@ -5000,8 +5536,13 @@ impl WorkspaceStore for PgBackend {
             .await
     }
 
-    async fn update_document(&self, id: Uuid, content: &str) -> Result<(), WorkspaceError> {
-        self.repo.update_document(id, content).await
+    async fn update_document(
+        &self,
+        id: Uuid,
+        content: &str,
+        if_match: Option<&str>,
+    ) -> Result<(), WorkspaceError> {
+        self.repo.update_document(id, content, if_match).await
     }
 
     async fn delete_document_by_path(
@@ -5015,6 +5556,72 @@ impl WorkspaceStore for PgBackend {
             .await
     }
 
+    async fn trash_document_by_path(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        path: &str,
+        deleted_by: &str,
+    ) -> Result<TrashedDocument, WorkspaceError> {
+        self.repo
+            .trash_document_by_path(user_id, agent_id, path, deleted_by)
+            .await
+    }
+
+    async fn list_trashed_documents(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+    ) -> Result<Vec<TrashedDocument>, WorkspaceError> {
+        self.repo.list_trashed_documents(user_id, agent_id).await
+    }
+
+    async fn get_trashed_document(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        id: Uuid,
+    ) -> Result<Option<TrashedDocument>, WorkspaceError> {
+        self.repo.get_trashed_document(user_id, agent_id, id).await
+    }
+
+    async fn delete_trashed_document(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        id: Uuid,
+    ) -> Result<bool, WorkspaceError> {
+        self.repo
+            .delete_trashed_document(user_id, agent_id, id)
+            .await
+    }
+
+    async fn purge_trashed_documents(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<TrashedDocument>, WorkspaceError> {
+        self.repo
+            .purge_trashed_documents(user_id, agent_id, cutoff)
+            .await
+    }
+
+    async fn list_document_revisions(
+        &self,
+        document_id: Uuid,
+    ) -> Result<Vec<DocumentRevision>, WorkspaceError> {
+        self.repo.list_document_revisions(document_id).await
+    }
+
+    async fn get_document_revision(
+        &self,
+        document_id: Uuid,
+        revision: i64,
+    ) -> Result<Option<DocumentRevision>, WorkspaceError> {
+        self.repo.get_document_revision(document_id, revision).await
+    }
+
     async fn list_directory(
         &self,
         user_id: &str,
@@ -5075,6 +5682,15 @@ impl WorkspaceStore for PgBackend {
             .await
     }
 
+    async fn get_chunk(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        chunk_id: Uuid,
+    ) -> Result<Option<CitedChunk>, WorkspaceError> {
+        self.repo.get_chunk(user_id, agent_id, chunk_id).await
+    }
+
     async fn hybrid_search(
         &self,
         user_id: &str,
@@ -5087,4 +5703,15 @@ impl WorkspaceStore for PgBackend {
             .hybrid_search(user_id, agent_id, query, embedding, config)
             .await
     }
+
+    async fn storage_usage(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        path_prefix: Option<&str>,
+    ) -> Result<WorkspaceStorageUsage, WorkspaceError> {
+        self.repo
+            .storage_usage(user_id, agent_id, path_prefix)
+            .await
+    }
 }
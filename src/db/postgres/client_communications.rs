@@ -0,0 +1,180 @@
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+
+use crate::db::{
+    ClientCommunicationConsentRecord, ClientCommunicationPreferencesRecord,
+    ClientCommunicationStore, CommunicationChannel, CommunicationConsentStatus,
+    CreateClientCommunicationConsentParams, UpsertClientCommunicationPreferencesParams,
+};
+use crate::error::DatabaseError;
+
+use super::PgBackend;
+
+const PREFERENCES_COLUMNS: &str = "user_id, client_id, allowed_channels, quiet_hours, timezone, \
+     language, disclaimers, updated_by, created_at, updated_at";
+
+const CONSENT_COLUMNS: &str =
+    "id, user_id, client_id, channel, status, source, consented_at, recorded_by, created_at";
+
+fn json_column<T: DeserializeOwned>(
+    row: &tokio_postgres::Row,
+    column: &str,
+) -> Result<T, DatabaseError> {
+    let value: serde_json::Value = row.get(column);
+    serde_json::from_value(value)
+        .map_err(|e| DatabaseError::Serialization(format!("invalid {column}: {e}")))
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<serde_json::Value, DatabaseError> {
+    serde_json::to_value(value).map_err(|e| DatabaseError::Serialization(e.to_string()))
+}
+
+fn row_to_preferences(
+    row: &tokio_postgres::Row,
+) -> Result<ClientCommunicationPreferencesRecord, DatabaseError> {
+    Ok(ClientCommunicationPreferencesRecord {
+        user_id: row.get("user_id"),
+        client_id: row.get("client_id"),
+        allowed_channels: json_column(row, "allowed_channels")?,
+        quiet_hours: json_column(row, "quiet_hours")?,
+        timezone: row.get("timezone"),
+        language: row.get("language"),
+        disclaimers: json_column(row, "disclaimers")?,
+        updated_by: row.get("updated_by"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
+fn row_to_consent(
+    row: &tokio_postgres::Row,
+) -> Result<ClientCommunicationConsentRecord, DatabaseError> {
+    let channel_raw: String = row.get("channel");
+    let status_raw: String = row.get("status");
+    Ok(ClientCommunicationConsentRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        client_id: row.get("client_id"),
+        channel: CommunicationChannel::from_db_value(&channel_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("unknown communication channel '{channel_raw}'"))
+        })?,
+        status: CommunicationConsentStatus::from_db_value(&status_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("unknown consent status '{status_raw}'"))
+        })?,
+        source: row.get("source"),
+        consented_at: row.get("consented_at"),
+        recorded_by: row.get("recorded_by"),
+        created_at: row.get("created_at"),
+    })
+}
+
+#[async_trait]
+impl ClientCommunicationStore for PgBackend {
+    async fn get_client_communication_preferences(
+        &self,
+        user_id: &str,
+        client_id: Uuid,
+    ) -> Result<Option<ClientCommunicationPreferencesRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "SELECT {PREFERENCES_COLUMNS} FROM client_communication_preferences \
+                     WHERE user_id = $1 AND client_id = $2"
+                ),
+                &[&user_id, &client_id],
+            )
+            .await?;
+        row.as_ref().map(row_to_preferences).transpose()
+    }
+
+    async fn upsert_client_communication_preferences(
+        &self,
+        user_id: &str,
+        client_id: Uuid,
+        input: &UpsertClientCommunicationPreferencesParams,
+    ) -> Result<ClientCommunicationPreferencesRecord, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                &format!(
+                    "INSERT INTO client_communication_preferences \
+                     (user_id, client_id, allowed_channels, quiet_hours, timezone, language, \
+                      disclaimers, updated_by) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+                     ON CONFLICT (user_id, client_id) DO UPDATE SET \
+                       allowed_channels = EXCLUDED.allowed_channels, \
+                       quiet_hours = EXCLUDED.quiet_hours, \
+                       timezone = EXCLUDED.timezone, \
+                       language = EXCLUDED.language, \
+                       disclaimers = EXCLUDED.disclaimers, \
+                       updated_by = EXCLUDED.updated_by, \
+                       updated_at = NOW() \
+                     RETURNING {PREFERENCES_COLUMNS}"
+                ),
+                &[
+                    &user_id,
+                    &client_id,
+                    &to_json(&input.allowed_channels)?,
+                    &to_json(&input.quiet_hours)?,
+                    &input.timezone,
+                    &input.language,
+                    &to_json(&input.disclaimers)?,
+                    &input.updated_by,
+                ],
+            )
+            .await?;
+        row_to_preferences(&row)
+    }
+
+    async fn create_client_communication_consent(
+        &self,
+        user_id: &str,
+        client_id: Uuid,
+        input: &CreateClientCommunicationConsentParams,
+    ) -> Result<ClientCommunicationConsentRecord, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                &format!(
+                    "INSERT INTO client_communication_consents \
+                     (id, user_id, client_id, channel, status, source, consented_at, \
+                      recorded_by) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+                     RETURNING {CONSENT_COLUMNS}"
+                ),
+                &[
+                    &Uuid::new_v4(),
+                    &user_id,
+                    &client_id,
+                    &input.channel.as_str(),
+                    &input.status.as_str(),
+                    &input.source,
+                    &input.consented_at,
+                    &input.recorded_by,
+                ],
+            )
+            .await?;
+        row_to_consent(&row)
+    }
+
+    async fn list_client_communication_consents(
+        &self,
+        user_id: &str,
+        client_id: Uuid,
+    ) -> Result<Vec<ClientCommunicationConsentRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {CONSENT_COLUMNS} FROM client_communication_consents \
+                     WHERE user_id = $1 AND client_id = $2 \
+                     ORDER BY consented_at DESC, created_at DESC"
+                ),
+                &[&user_id, &client_id],
+            )
+            .await?;
+        rows.iter().map(row_to_consent).collect()
+    }
+}
@@ -0,0 +1,177 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::db::{
+    CostEntryRecord, CostLedgerStore, CostSource, MatterSpendCapRecord, RecordCostEntryParams,
+};
+use crate::error::DatabaseError;
+
+use super::PgBackend;
+
+const COST_ENTRY_COLUMNS: &str = "id, user_id, matter_id, client_id, source, job_id, label, \
+     input_tokens, output_tokens, cost, created_at";
+
+fn row_to_cost_entry(row: &tokio_postgres::Row) -> Result<CostEntryRecord, DatabaseError> {
+    let source_raw: String = row.get("source");
+    let source = CostSource::from_db_value(&source_raw).ok_or_else(|| {
+        DatabaseError::Serialization(format!("invalid cost source '{source_raw}'"))
+    })?;
+    Ok(CostEntryRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        matter_id: row.get("matter_id"),
+        client_id: row.get("client_id"),
+        source,
+        job_id: row.get("job_id"),
+        label: row.get("label"),
+        input_tokens: row.get("input_tokens"),
+        output_tokens: row.get("output_tokens"),
+        cost: row.get("cost"),
+        created_at: row.get("created_at"),
+    })
+}
+
+fn row_to_spend_cap(row: &tokio_postgres::Row) -> MatterSpendCapRecord {
+    MatterSpendCapRecord {
+        user_id: row.get("user_id"),
+        matter_id: row.get("matter_id"),
+        monthly_cap_cents: row.get("monthly_cap_cents"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+#[async_trait]
+impl CostLedgerStore for PgBackend {
+    async fn record_cost_entry(
+        &self,
+        params: &RecordCostEntryParams,
+    ) -> Result<CostEntryRecord, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                &format!(
+                    "INSERT INTO cost_entries \
+                     (id, user_id, matter_id, client_id, source, job_id, label, \
+                      input_tokens, output_tokens, cost) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) \
+                     RETURNING {COST_ENTRY_COLUMNS}"
+                ),
+                &[
+                    &Uuid::new_v4(),
+                    &params.user_id,
+                    &params.matter_id,
+                    &params.client_id,
+                    &params.source.as_str(),
+                    &params.job_id,
+                    &params.label,
+                    &i64::from(params.input_tokens),
+                    &i64::from(params.output_tokens),
+                    &params.cost,
+                ],
+            )
+            .await?;
+        row_to_cost_entry(&row)
+    }
+
+    async fn list_cost_entries(
+        &self,
+        user_id: &str,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CostEntryRecord>, DatabaseError> {
+        let conn = self.store.read_conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {COST_ENTRY_COLUMNS} FROM cost_entries \
+                     WHERE user_id = $1 \
+                       AND ($2::timestamptz IS NULL OR created_at >= $2) \
+                       AND ($3::timestamptz IS NULL OR created_at < $3) \
+                     ORDER BY created_at ASC"
+                ),
+                &[&user_id, &since, &until],
+            )
+            .await?;
+        rows.iter().map(row_to_cost_entry).collect()
+    }
+
+    async fn matter_cost_since(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Decimal, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                "SELECT COALESCE(SUM(cost), 0) AS total FROM cost_entries \
+                 WHERE user_id = $1 AND matter_id = $2 AND created_at >= $3",
+                &[&user_id, &matter_id, &since],
+            )
+            .await?;
+        Ok(row.get("total"))
+    }
+
+    async fn set_matter_spend_cap(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        monthly_cap_cents: Option<i64>,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.store.conn().await?;
+        match monthly_cap_cents {
+            Some(cap) => {
+                conn.execute(
+                    "INSERT INTO matter_spend_caps (user_id, matter_id, monthly_cap_cents) \
+                     VALUES ($1, $2, $3) \
+                     ON CONFLICT (user_id, matter_id) DO UPDATE SET \
+                       monthly_cap_cents = EXCLUDED.monthly_cap_cents, \
+                       updated_at = NOW()",
+                    &[&user_id, &matter_id, &cap],
+                )
+                .await?
+            }
+            None => {
+                conn.execute(
+                    "DELETE FROM matter_spend_caps WHERE user_id = $1 AND matter_id = $2",
+                    &[&user_id, &matter_id],
+                )
+                .await?
+            }
+        };
+        Ok(())
+    }
+
+    async fn get_matter_spend_cap(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<Option<MatterSpendCapRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                "SELECT user_id, matter_id, monthly_cap_cents, updated_at \
+                 FROM matter_spend_caps WHERE user_id = $1 AND matter_id = $2",
+                &[&user_id, &matter_id],
+            )
+            .await?;
+        Ok(row.as_ref().map(row_to_spend_cap))
+    }
+
+    async fn list_matter_spend_caps(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<MatterSpendCapRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                "SELECT user_id, matter_id, monthly_cap_cents, updated_at \
+                 FROM matter_spend_caps WHERE user_id = $1 ORDER BY matter_id",
+                &[&user_id],
+            )
+            .await?;
+        Ok(rows.iter().map(row_to_spend_cap).collect())
+    }
+}
@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::{
+    CreateDocumentEntityParams, DocumentEntityKind, DocumentEntityRecord, DocumentEntityStore,
+};
+use crate::error::DatabaseError;
+
+use super::PgBackend;
+
+const DOCUMENT_ENTITY_COLUMNS: &str = "id, user_id, matter_id, kind, value, normalized_value, \
+     source_path, snippet, confidence, extractor, created_at";
+
+fn row_to_document_entity(
+    row: &tokio_postgres::Row,
+) -> Result<DocumentEntityRecord, DatabaseError> {
+    let kind_raw: String = row.get("kind");
+    let kind = DocumentEntityKind::from_db_value(&kind_raw).ok_or_else(|| {
+        DatabaseError::Serialization(format!("invalid document entity kind '{kind_raw}'"))
+    })?;
+    Ok(DocumentEntityRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        matter_id: row.get("matter_id"),
+        kind,
+        value: row.get("value"),
+        normalized_value: row.get("normalized_value"),
+        source_path: row.get("source_path"),
+        snippet: row.get("snippet"),
+        confidence: row.get("confidence"),
+        extractor: row.get("extractor"),
+        created_at: row.get("created_at"),
+    })
+}
+
+#[async_trait]
+impl DocumentEntityStore for PgBackend {
+    async fn replace_document_entities(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        source_path: &str,
+        entities: &[CreateDocumentEntityParams],
+    ) -> Result<usize, DatabaseError> {
+        let mut conn = self.store.conn().await?;
+        let tx = conn.transaction().await?;
+        tx.execute(
+            "DELETE FROM document_entities \
+             WHERE user_id = $1 AND matter_id = $2 AND source_path = $3",
+            &[&user_id, &matter_id, &source_path],
+        )
+        .await?;
+        let mut inserted = 0;
+        for entity in entities {
+            inserted += tx
+                .execute(
+                    "INSERT INTO document_entities \
+                     (id, user_id, matter_id, kind, value, normalized_value, source_path, \
+                      snippet, confidence, extractor) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) \
+                     ON CONFLICT (user_id, matter_id, source_path, kind, normalized_value) \
+                     DO NOTHING",
+                    &[
+                        &Uuid::new_v4(),
+                        &user_id,
+                        &matter_id,
+                        &entity.kind.as_str(),
+                        &entity.value,
+                        &entity.normalized_value,
+                        &source_path,
+                        &entity.snippet,
+                        &entity.confidence,
+                        &entity.extractor,
+                    ],
+                )
+                .await? as usize;
+        }
+        tx.commit().await?;
+        Ok(inserted)
+    }
+
+    async fn list_document_entities(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        kind: Option<DocumentEntityKind>,
+    ) -> Result<Vec<DocumentEntityRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let kind_filter = kind.map(|k| k.as_str().to_string());
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {DOCUMENT_ENTITY_COLUMNS} FROM document_entities \
+                     WHERE user_id = $1 AND matter_id = $2 AND ($3::text IS NULL OR kind = $3) \
+                     ORDER BY kind, normalized_value, source_path"
+                ),
+                &[&user_id, &matter_id, &kind_filter],
+            )
+            .await?;
+        rows.iter().map(row_to_document_entity).collect()
+    }
+}
@@ -0,0 +1,171 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::{DocumentShareLinkRecord, DocumentShareStore, NewDocumentShareLinkParams};
+use crate::error::DatabaseError;
+
+use super::PgBackend;
+
+const SHARE_LINK_COLUMNS: &str = "id, user_id, path, matter_id, token_hash, password_hash, \
+     recipient, watermark, created_by, expires_at, revoked_at, download_count, \
+     last_downloaded_at, failed_password_attempts, last_password_failure_at, created_at";
+
+fn row_to_share_link(row: &tokio_postgres::Row) -> DocumentShareLinkRecord {
+    DocumentShareLinkRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        path: row.get("path"),
+        matter_id: row.get("matter_id"),
+        token_hash: row.get("token_hash"),
+        password_hash: row.get("password_hash"),
+        recipient: row.get("recipient"),
+        watermark: row.get("watermark"),
+        created_by: row.get("created_by"),
+        expires_at: row.get("expires_at"),
+        revoked_at: row.get("revoked_at"),
+        download_count: row.get("download_count"),
+        last_downloaded_at: row.get("last_downloaded_at"),
+        failed_password_attempts: row.get("failed_password_attempts"),
+        last_password_failure_at: row.get("last_password_failure_at"),
+        created_at: row.get("created_at"),
+    }
+}
+
+#[async_trait]
+impl DocumentShareStore for PgBackend {
+    async fn create_document_share_link(
+        &self,
+        params: &NewDocumentShareLinkParams,
+    ) -> Result<DocumentShareLinkRecord, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                &format!(
+                    "INSERT INTO document_share_links \
+                     (id, user_id, path, matter_id, token_hash, password_hash, recipient, \
+                      watermark, created_by, expires_at) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) \
+                     RETURNING {SHARE_LINK_COLUMNS}"
+                ),
+                &[
+                    &Uuid::new_v4(),
+                    &params.user_id,
+                    &params.path,
+                    &params.matter_id,
+                    &params.token_hash,
+                    &params.password_hash,
+                    &params.recipient,
+                    &params.watermark,
+                    &params.created_by,
+                    &params.expires_at,
+                ],
+            )
+            .await?;
+        Ok(row_to_share_link(&row))
+    }
+
+    async fn get_document_share_link_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<DocumentShareLinkRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "SELECT {SHARE_LINK_COLUMNS} FROM document_share_links WHERE token_hash = $1"
+                ),
+                &[&token_hash],
+            )
+            .await?;
+        Ok(row.as_ref().map(row_to_share_link))
+    }
+
+    async fn list_document_share_links(
+        &self,
+        user_id: &str,
+        path: Option<&str>,
+    ) -> Result<Vec<DocumentShareLinkRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {SHARE_LINK_COLUMNS} FROM document_share_links \
+                     WHERE user_id = $1 AND ($2::text IS NULL OR path = $2) \
+                     ORDER BY created_at DESC"
+                ),
+                &[&user_id, &path],
+            )
+            .await?;
+        Ok(rows.iter().map(row_to_share_link).collect())
+    }
+
+    async fn get_document_share_link(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<DocumentShareLinkRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "SELECT {SHARE_LINK_COLUMNS} FROM document_share_links \
+                     WHERE user_id = $1 AND id = $2"
+                ),
+                &[&user_id, &id],
+            )
+            .await?;
+        Ok(row.as_ref().map(row_to_share_link))
+    }
+
+    async fn revoke_document_share_link(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<DocumentShareLinkRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "UPDATE document_share_links SET revoked_at = COALESCE(revoked_at, NOW()) \
+                     WHERE user_id = $1 AND id = $2 RETURNING {SHARE_LINK_COLUMNS}"
+                ),
+                &[&user_id, &id],
+            )
+            .await?;
+        Ok(row.as_ref().map(row_to_share_link))
+    }
+
+    async fn record_document_share_download(&self, id: Uuid) -> Result<(), DatabaseError> {
+        let conn = self.store.conn().await?;
+        conn.execute(
+            "UPDATE document_share_links \
+             SET download_count = download_count + 1, last_downloaded_at = NOW(), \
+                 failed_password_attempts = 0, last_password_failure_at = NULL \
+             WHERE id = $1",
+            &[&id],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn claim_document_share_password_attempt(
+        &self,
+        id: Uuid,
+        seen_attempts: i64,
+    ) -> Result<Option<DocumentShareLinkRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "UPDATE document_share_links \
+                     SET failed_password_attempts = failed_password_attempts + 1, \
+                         last_password_failure_at = NOW() \
+                     WHERE id = $1 AND failed_password_attempts = $2 \
+                     RETURNING {SHARE_LINK_COLUMNS}"
+                ),
+                &[&id, &seen_attempts],
+            )
+            .await?;
+        Ok(row.as_ref().map(row_to_share_link))
+    }
+}
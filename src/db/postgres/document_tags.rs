@@ -0,0 +1,118 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::{DocumentTagRecord, DocumentTagStore, SetDocumentTagParams};
+use crate::error::DatabaseError;
+
+use super::PgBackend;
+
+const DOCUMENT_TAG_COLUMNS: &str = "id, user_id, path, key, value, created_by, created_at, \
+     updated_at";
+
+fn row_to_document_tag(row: &tokio_postgres::Row) -> DocumentTagRecord {
+    DocumentTagRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        path: row.get("path"),
+        key: row.get("key"),
+        value: row.get("value"),
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+#[async_trait]
+impl DocumentTagStore for PgBackend {
+    async fn set_document_tags(
+        &self,
+        user_id: &str,
+        path: &str,
+        tags: &[SetDocumentTagParams],
+        created_by: &str,
+    ) -> Result<Vec<DocumentTagRecord>, DatabaseError> {
+        let mut conn = self.store.conn().await?;
+        let tx = conn.transaction().await?;
+        for tag in tags {
+            tx.execute(
+                "INSERT INTO document_tags (id, user_id, path, key, value, created_by) \
+                 VALUES ($1, $2, $3, $4, $5, $6) \
+                 ON CONFLICT (user_id, path, key) DO UPDATE SET \
+                 value = EXCLUDED.value, updated_at = NOW()",
+                &[
+                    &Uuid::new_v4(),
+                    &user_id,
+                    &path,
+                    &tag.key,
+                    &tag.value,
+                    &created_by,
+                ],
+            )
+            .await?;
+        }
+        let rows = tx
+            .query(
+                &format!(
+                    "SELECT {DOCUMENT_TAG_COLUMNS} FROM document_tags \
+                     WHERE user_id = $1 AND path = $2 ORDER BY key"
+                ),
+                &[&user_id, &path],
+            )
+            .await?;
+        tx.commit().await?;
+        Ok(rows.iter().map(row_to_document_tag).collect())
+    }
+
+    async fn remove_document_tag(
+        &self,
+        user_id: &str,
+        path: &str,
+        key: &str,
+    ) -> Result<bool, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let removed = conn
+            .execute(
+                "DELETE FROM document_tags WHERE user_id = $1 AND path = $2 AND key = $3",
+                &[&user_id, &path, &key],
+            )
+            .await?;
+        Ok(removed > 0)
+    }
+
+    async fn list_document_tags(
+        &self,
+        user_id: &str,
+        path: &str,
+    ) -> Result<Vec<DocumentTagRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {DOCUMENT_TAG_COLUMNS} FROM document_tags \
+                     WHERE user_id = $1 AND path = $2 ORDER BY key"
+                ),
+                &[&user_id, &path],
+            )
+            .await?;
+        Ok(rows.iter().map(row_to_document_tag).collect())
+    }
+
+    async fn list_tagged_documents(
+        &self,
+        user_id: &str,
+        path_prefix: Option<&str>,
+    ) -> Result<Vec<DocumentTagRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {DOCUMENT_TAG_COLUMNS} FROM document_tags \
+                     WHERE user_id = $1 AND ($2::text IS NULL OR starts_with(path, $2)) \
+                     ORDER BY path, key"
+                ),
+                &[&user_id, &path_prefix],
+            )
+            .await?;
+        Ok(rows.iter().map(row_to_document_tag).collect())
+    }
+}
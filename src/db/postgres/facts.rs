@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::{CreateMatterFactParams, FactStore, MatterFactQuery, MatterFactRecord};
+use crate::error::DatabaseError;
+
+use super::PgBackend;
+
+const MATTER_FACT_COLUMNS: &str = "id, user_id, matter_id, fact_date, description, source_path, \
+     source_locator, source_excerpt, confidence, extractor, created_by, created_at, updated_at";
+
+fn row_to_matter_fact(row: &tokio_postgres::Row) -> MatterFactRecord {
+    MatterFactRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        matter_id: row.get("matter_id"),
+        fact_date: row.get("fact_date"),
+        description: row.get("description"),
+        source_path: row.get("source_path"),
+        source_locator: row.get("source_locator"),
+        source_excerpt: row.get("source_excerpt"),
+        confidence: row.get("confidence"),
+        extractor: row.get("extractor"),
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+#[async_trait]
+impl FactStore for PgBackend {
+    async fn create_matter_fact(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        params: &CreateMatterFactParams,
+    ) -> Result<MatterFactRecord, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                &format!(
+                    "INSERT INTO matter_facts \
+                     (id, user_id, matter_id, fact_date, description, source_path, \
+                      source_locator, source_excerpt, confidence, extractor, created_by) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) \
+                     RETURNING {MATTER_FACT_COLUMNS}"
+                ),
+                &[
+                    &Uuid::new_v4(),
+                    &user_id,
+                    &matter_id,
+                    &params.fact_date,
+                    &params.description,
+                    &params.source_path,
+                    &params.source_locator,
+                    &params.source_excerpt,
+                    &params.confidence,
+                    &params.extractor,
+                    &params.created_by,
+                ],
+            )
+            .await?;
+        Ok(row_to_matter_fact(&row))
+    }
+
+    async fn list_matter_facts(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        query: &MatterFactQuery,
+    ) -> Result<Vec<MatterFactRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {MATTER_FACT_COLUMNS} FROM matter_facts \
+                     WHERE user_id = $1 AND matter_id = $2 \
+                       AND ($3::date IS NULL OR fact_date >= $3) \
+                       AND ($4::date IS NULL OR fact_date <= $4) \
+                       AND ($5::text IS NULL OR source_path = $5) \
+                       AND ($6::float8 IS NULL OR confidence >= $6) \
+                     ORDER BY fact_date ASC, created_at ASC, id ASC"
+                ),
+                &[
+                    &user_id,
+                    &matter_id,
+                    &query.from,
+                    &query.to,
+                    &query.source_path,
+                    &query.min_confidence,
+                ],
+            )
+            .await?;
+        Ok(rows.iter().map(row_to_matter_fact).collect())
+    }
+
+    async fn delete_matter_facts_for_source(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        source_path: &str,
+    ) -> Result<u64, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let deleted = conn
+            .execute(
+                "DELETE FROM matter_facts \
+                 WHERE user_id = $1 AND matter_id = $2 AND source_path = $3",
+                &[&user_id, &matter_id, &source_path],
+            )
+            .await?;
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,386 @@
+//! Connection acquisition with retry-with-backoff and read-replica failover.
+//!
+//! Every PostgreSQL query in `Store`, `Repository` and the `PgBackend`
+//! submodules starts by checking a connection out of the pool, so retrying
+//! here covers all `Database` methods: a primary restart, a brief network
+//! partition or a saturated `max_connections` is retried a few times with
+//! exponential backoff instead of failing the request. Errors that exhaust the
+//! retries are logged with their cause and surface to callers as
+//! [`DatabaseError::Unavailable`] rather than raw pool errors.
+//!
+//! When `DATABASE_READ_URL` is set, heavy read paths (search, listings,
+//! reporting) call [`PgPools::read_conn`], which prefers the replica. If the
+//! replica can't hand out a connection it is marked down for
+//! [`REPLICA_COOLDOWN`] and reads fall back to the primary; the next read
+//! after the cooldown tries the replica again (automatic failback). Reads
+//! routed to the replica must tolerate replication lag, so anything that
+//! reads its own writes keeps using [`PgPools::conn`].
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use deadpool_postgres::{Config, Object, Pool, PoolError, Runtime, Timeouts};
+use tokio_postgres::NoTls;
+use tokio_postgres::error::SqlState;
+
+use crate::config::DatabaseConfig;
+use crate::error::DatabaseError;
+
+/// How long a failed replica is skipped before reads try it again.
+pub const REPLICA_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Upper bound on waiting for, creating or recycling a replica connection, so
+/// a dead replica degrades to the primary instead of hanging reads.
+const REPLICA_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Backoff schedule for transient connection errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 disables retrying).
+    pub max_retries: u32,
+    /// Delay before the first retry; doubles on each subsequent retry.
+    pub base_delay: Duration,
+    /// Cap on a single delay.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `retry` (0-based).
+    pub fn delay(&self, retry: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay)
+    }
+}
+
+/// Run `op` until it succeeds, fails with a non-transient error, or runs out
+/// of retries. On failure returns the last error and the number of attempts.
+pub async fn retry_transient<T, E, F, Fut>(
+    policy: &RetryPolicy,
+    is_transient: impl Fn(&E) -> bool,
+    mut op: F,
+) -> Result<T, (E, u32)>
+where
+    E: std::fmt::Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut retry = 0;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) if retry < policy.max_retries && is_transient(&e) => {
+                let delay = policy.delay(retry);
+                retry += 1;
+                tracing::warn!(
+                    "Transient database error (attempt {}/{}), retrying in {:?}: {}",
+                    retry,
+                    policy.max_retries + 1,
+                    delay,
+                    e
+                );
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => return Err((e, retry + 1)),
+        }
+    }
+}
+
+/// Whether a pool checkout error is worth retrying.
+pub fn is_transient_pool_error(err: &PoolError) -> bool {
+    match err {
+        PoolError::Timeout(_) => true,
+        PoolError::Backend(e) => is_transient_pg_error(e),
+        PoolError::Closed | PoolError::NoRuntimeSpecified | PoolError::PostCreateHook(_) => false,
+    }
+}
+
+/// Whether a PostgreSQL error reflects a connectivity problem rather than a
+/// bad query or bad credentials.
+pub fn is_transient_pg_error(err: &tokio_postgres::Error) -> bool {
+    if let Some(code) = err.code() {
+        return [
+            SqlState::CONNECTION_EXCEPTION,
+            SqlState::CONNECTION_DOES_NOT_EXIST,
+            SqlState::CONNECTION_FAILURE,
+            SqlState::SQLCLIENT_UNABLE_TO_ESTABLISH_SQLCONNECTION,
+            SqlState::TOO_MANY_CONNECTIONS,
+            SqlState::ADMIN_SHUTDOWN,
+            SqlState::CRASH_SHUTDOWN,
+            SqlState::CANNOT_CONNECT_NOW,
+        ]
+        .contains(code);
+    }
+    // No SQLSTATE: either the socket went away or connecting failed at the
+    // I/O level (refused, reset, DNS).
+    err.is_closed()
+        || std::error::Error::source(err).is_some_and(|source| source.is::<std::io::Error>())
+}
+
+fn unavailable(err: &PoolError, attempts: u32, which: &str) -> DatabaseError {
+    tracing::error!(
+        "Could not get a {} database connection after {} attempt(s): {}",
+        which,
+        attempts,
+        err
+    );
+    if is_transient_pool_error(err) {
+        DatabaseError::Unavailable(format!(
+            "{which} database unreachable after {attempts} attempt(s)"
+        ))
+    } else {
+        DatabaseError::Unavailable(format!("{which} database connection failed"))
+    }
+}
+
+struct Replica {
+    pool: Pool,
+    down_until: Mutex<Option<Instant>>,
+}
+
+impl Replica {
+    fn is_up(&self, now: Instant) -> bool {
+        match self.down_until.lock() {
+            Ok(guard) => guard.is_none_or(|until| now >= until),
+            Err(_) => false,
+        }
+    }
+
+    /// Record the outcome of a checkout. Returns true when the replica's
+    /// state changed, so transitions are logged once.
+    fn record(&self, ok: bool, now: Instant) -> bool {
+        let Ok(mut guard) = self.down_until.lock() else {
+            return false;
+        };
+        let was_down = guard.is_some();
+        *guard = if ok {
+            None
+        } else {
+            Some(now + REPLICA_COOLDOWN)
+        };
+        was_down == ok
+    }
+}
+
+/// Primary pool plus optional read replica, shared by `Store` and the
+/// workspace `Repository`.
+#[derive(Clone)]
+pub struct PgPools {
+    primary: Pool,
+    replica: Option<Arc<Replica>>,
+    retry: RetryPolicy,
+}
+
+impl PgPools {
+    /// Wrap an existing primary pool with the default retry policy and no
+    /// replica.
+    pub fn from_pool(pool: Pool) -> Self {
+        Self {
+            primary: pool,
+            replica: None,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Build the primary pool (and replica pool, if configured) from config.
+    pub fn from_config(config: &DatabaseConfig) -> Result<Self, DatabaseError> {
+        let primary = build_pool(config.url(), config.pool_size, Timeouts::default())?;
+        let replica = match config.read_url() {
+            Some(url) => Some(Arc::new(Replica {
+                pool: build_pool(
+                    url,
+                    config.pool_size,
+                    Timeouts {
+                        wait: Some(REPLICA_TIMEOUT),
+                        create: Some(REPLICA_TIMEOUT),
+                        recycle: Some(REPLICA_TIMEOUT),
+                    },
+                )?,
+                down_until: Mutex::new(None),
+            })),
+            None => None,
+        };
+        Ok(Self {
+            primary,
+            replica,
+            retry: RetryPolicy {
+                max_retries: config.connect_retries,
+                ..RetryPolicy::default()
+            },
+        })
+    }
+
+    /// The primary pool.
+    pub fn primary(&self) -> &Pool {
+        &self.primary
+    }
+
+    /// Whether a read replica is configured.
+    pub fn has_replica(&self) -> bool {
+        self.replica.is_some()
+    }
+
+    /// Check out a primary connection, retrying transient failures.
+    pub async fn conn(&self) -> Result<Object, DatabaseError> {
+        retry_transient(&self.retry, is_transient_pool_error, || self.primary.get())
+            .await
+            .map_err(|(e, attempts)| unavailable(&e, attempts, "primary"))
+    }
+
+    /// Check out a connection for a lag-tolerant read: the replica when one is
+    /// configured and healthy, otherwise the primary.
+    pub async fn read_conn(&self) -> Result<Object, DatabaseError> {
+        if let Some(replica) = &self.replica
+            && replica.is_up(Instant::now())
+        {
+            match replica.pool.get().await {
+                Ok(conn) => {
+                    if replica.record(true, Instant::now()) {
+                        tracing::info!("Read replica recovered; routing reads to it again");
+                    }
+                    return Ok(conn);
+                }
+                Err(e) => {
+                    if replica.record(false, Instant::now()) {
+                        tracing::warn!(
+                            "Read replica unavailable, falling back to primary for {:?}: {}",
+                            REPLICA_COOLDOWN,
+                            e
+                        );
+                    }
+                }
+            }
+        }
+        self.conn().await
+    }
+}
+
+fn build_pool(url: &str, max_size: usize, timeouts: Timeouts) -> Result<Pool, DatabaseError> {
+    let mut cfg = Config::new();
+    cfg.url = Some(url.to_string());
+    cfg.pool = Some(deadpool_postgres::PoolConfig {
+        max_size,
+        timeouts,
+        ..Default::default()
+    });
+    cfg.create_pool(Some(Runtime::Tokio1), NoTls)
+        .map_err(|e| DatabaseError::Pool(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use deadpool_postgres::TimeoutType;
+
+    use super::*;
+
+    fn fast_policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(250),
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        let policy = fast_policy(5);
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(1), Duration::from_millis(200));
+        assert_eq!(policy.delay(2), Duration::from_millis(250));
+        assert_eq!(policy.delay(40), Duration::from_millis(250));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retries_transient_errors_until_success() {
+        let calls = AtomicU32::new(0);
+        let result = retry_transient(
+            &fast_policy(3),
+            |e: &String| e == "transient",
+            || async {
+                if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err("transient".to_string())
+                } else {
+                    Ok(7)
+                }
+            },
+        )
+        .await;
+        assert_eq!(result, Ok(7));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn gives_up_after_max_retries() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = retry_transient(
+            &fast_policy(2),
+            |_: &String| true,
+            || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err("down".to_string())
+            },
+        )
+        .await;
+        assert_eq!(result, Err(("down".to_string(), 3)));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn permanent_errors_are_not_retried() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = retry_transient(
+            &fast_policy(5),
+            |e: &String| e == "transient",
+            || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err("bad password".to_string())
+            },
+        )
+        .await;
+        assert_eq!(result, Err(("bad password".to_string(), 1)));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn pool_error_classification() {
+        assert!(is_transient_pool_error(&PoolError::Timeout(
+            TimeoutType::Wait
+        )));
+        assert!(!is_transient_pool_error(&PoolError::Closed));
+        assert!(!is_transient_pool_error(&PoolError::NoRuntimeSpecified));
+    }
+
+    #[tokio::test]
+    async fn replica_marked_down_then_fails_back_after_cooldown() {
+        let replica = Replica {
+            pool: build_pool("postgres://localhost/unused", 1, Timeouts::default())
+                .expect("pool config"),
+            down_until: Mutex::new(None),
+        };
+        let start = Instant::now();
+        assert!(replica.is_up(start));
+
+        assert!(replica.record(false, start), "up -> down is a transition");
+        assert!(!replica.is_up(start + Duration::from_secs(1)));
+        assert!(replica.is_up(start + REPLICA_COOLDOWN));
+
+        assert!(replica.record(true, start + REPLICA_COOLDOWN));
+        assert!(
+            !replica.record(true, start + REPLICA_COOLDOWN),
+            "staying up is not a transition"
+        );
+    }
+}
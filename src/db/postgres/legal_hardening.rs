@@ -5,13 +5,16 @@ use uuid::Uuid;
 use crate::db::{
     BillingRateScheduleRecord, BillingRateStore, CitationVerificationResultRecord,
     CitationVerificationRunRecord, CitationVerificationStatus, CitationVerificationStore,
+    ClientStatusReportRecord, ClientStatusReportStatus, ClientStatusReportStore,
     ComputeTrustReconciliationParams, CreateBillingRateScheduleParams,
     CreateCitationVerificationResultParams, CreateCitationVerificationRunParams,
     CreateTrustStatementImportParams, CreateTrustStatementLineParams, DocumentReadinessState,
-    MatterDocumentRecord, MatterDocumentStore, TrustAccountRecord, TrustAccountingStore,
-    TrustLedgerEntryRecord, TrustReconciliationRecord, TrustReconciliationStatus,
-    TrustStatementImportRecord, TrustStatementLineRecord, UpdateBillingRateScheduleParams,
-    UpsertTrustAccountParams,
+    DocumentTranslationRecord, DocumentTranslationStore, MatterDocumentRecord, MatterDocumentStore,
+    TranslationStatus, TrustAccountRecord, TrustAccountingStore, TrustLedgerEntryRecord,
+    TrustReconciliationRecord, TrustReconciliationStatus, TrustStatementImportRecord,
+    TrustStatementLineRecord, UpdateBillingRateScheduleParams, UpdateClientStatusReportParams,
+    UpdateDocumentTranslationStatusParams, UpsertClientStatusReportParams,
+    UpsertDocumentTranslationParams, UpsertTrustAccountParams,
 };
 use crate::error::DatabaseError;
 
@@ -48,6 +51,60 @@ fn row_to_citation_run(
     })
 }
 
+const DOCUMENT_TRANSLATION_COLUMNS: &str = "id, user_id, matter_id, source_path, source_language, target_language, translation_path, provider, status, translator, certified_path, certified_at, created_by, created_at, updated_at";
+
+fn row_to_document_translation(
+    row: &tokio_postgres::Row,
+) -> Result<DocumentTranslationRecord, DatabaseError> {
+    let status_raw: String = row.get("status");
+    Ok(DocumentTranslationRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        matter_id: row.get("matter_id"),
+        source_path: row.get("source_path"),
+        source_language: row.get("source_language"),
+        target_language: row.get("target_language"),
+        translation_path: row.get("translation_path"),
+        provider: row.get("provider"),
+        status: TranslationStatus::from_db_value(&status_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("invalid translation status '{status_raw}'"))
+        })?,
+        translator: row.get("translator"),
+        certified_path: row.get("certified_path"),
+        certified_at: row.get("certified_at"),
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
+const CLIENT_STATUS_REPORT_COLUMNS: &str = "id, user_id, matter_id, report_path, subject, recipient, status, reviewed_by, review_note, reviewed_at, sent_at, delivery_error, created_by, created_at, updated_at";
+
+fn row_to_client_status_report(
+    row: &tokio_postgres::Row,
+) -> Result<ClientStatusReportRecord, DatabaseError> {
+    let status_raw: String = row.get("status");
+    Ok(ClientStatusReportRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        matter_id: row.get("matter_id"),
+        report_path: row.get("report_path"),
+        subject: row.get("subject"),
+        recipient: row.get("recipient"),
+        status: ClientStatusReportStatus::from_db_value(&status_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("invalid status report status '{status_raw}'"))
+        })?,
+        reviewed_by: row.get("reviewed_by"),
+        review_note: row.get("review_note"),
+        reviewed_at: row.get("reviewed_at"),
+        sent_at: row.get("sent_at"),
+        delivery_error: row.get("delivery_error"),
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
 fn row_to_citation_result(
     row: &tokio_postgres::Row,
 ) -> Result<CitationVerificationResultRecord, DatabaseError> {
@@ -422,6 +479,242 @@ impl CitationVerificationStore for PgBackend {
     }
 }
 
+#[async_trait::async_trait]
+impl DocumentTranslationStore for PgBackend {
+    async fn upsert_document_translation(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        input: &UpsertDocumentTranslationParams,
+    ) -> Result<DocumentTranslationRecord, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                &format!(
+                    "INSERT INTO document_translations \
+                     (id, user_id, matter_id, source_path, source_language, target_language, translation_path, provider, created_by) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) \
+                     ON CONFLICT (user_id, matter_id, source_path, target_language) DO UPDATE SET \
+                       source_language = EXCLUDED.source_language, \
+                       translation_path = EXCLUDED.translation_path, \
+                       provider = EXCLUDED.provider, \
+                       updated_at = NOW() \
+                     RETURNING {DOCUMENT_TRANSLATION_COLUMNS}"
+                ),
+                &[
+                    &Uuid::new_v4(),
+                    &user_id,
+                    &matter_id,
+                    &input.source_path,
+                    &input.source_language,
+                    &input.target_language,
+                    &input.translation_path,
+                    &input.provider,
+                    &input.created_by,
+                ],
+            )
+            .await?;
+        row_to_document_translation(&row)
+    }
+
+    async fn list_document_translations(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<Vec<DocumentTranslationRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {DOCUMENT_TRANSLATION_COLUMNS} FROM document_translations \
+                     WHERE user_id = $1 AND matter_id = $2 \
+                     ORDER BY updated_at DESC, source_path ASC"
+                ),
+                &[&user_id, &matter_id],
+            )
+            .await?;
+        rows.iter().map(row_to_document_translation).collect()
+    }
+
+    async fn get_document_translation(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        translation_id: Uuid,
+    ) -> Result<Option<DocumentTranslationRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "SELECT {DOCUMENT_TRANSLATION_COLUMNS} FROM document_translations \
+                     WHERE user_id = $1 AND matter_id = $2 AND id = $3"
+                ),
+                &[&user_id, &matter_id, &translation_id],
+            )
+            .await?;
+        row.map(|row| row_to_document_translation(&row)).transpose()
+    }
+
+    async fn update_document_translation_status(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        translation_id: Uuid,
+        input: &UpdateDocumentTranslationStatusParams,
+    ) -> Result<Option<DocumentTranslationRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let certified = input.status == TranslationStatus::Certified;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "UPDATE document_translations SET \
+                       status = $4, \
+                       translator = COALESCE($5, translator), \
+                       certified_path = CASE WHEN $7::boolean THEN $6::text ELSE NULL END, \
+                       certified_at = CASE WHEN $7::boolean THEN NOW() ELSE NULL END, \
+                       updated_at = NOW() \
+                     WHERE user_id = $1 AND matter_id = $2 AND id = $3 \
+                     RETURNING {DOCUMENT_TRANSLATION_COLUMNS}"
+                ),
+                &[
+                    &user_id,
+                    &matter_id,
+                    &translation_id,
+                    &input.status.as_str(),
+                    &input.translator,
+                    &input.certified_path,
+                    &certified,
+                ],
+            )
+            .await?;
+        row.map(|row| row_to_document_translation(&row)).transpose()
+    }
+}
+
+#[async_trait::async_trait]
+impl ClientStatusReportStore for PgBackend {
+    async fn upsert_client_status_report(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        input: &UpsertClientStatusReportParams,
+    ) -> Result<ClientStatusReportRecord, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                &format!(
+                    "INSERT INTO client_status_reports \
+                     (id, user_id, matter_id, report_path, subject, recipient, created_by) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7) \
+                     ON CONFLICT (user_id, matter_id, report_path) DO UPDATE SET \
+                       subject = EXCLUDED.subject, \
+                       recipient = EXCLUDED.recipient, \
+                       status = 'pending_approval', \
+                       reviewed_by = NULL, \
+                       review_note = NULL, \
+                       reviewed_at = NULL, \
+                       delivery_error = NULL, \
+                       updated_at = NOW() \
+                     RETURNING {CLIENT_STATUS_REPORT_COLUMNS}"
+                ),
+                &[
+                    &Uuid::new_v4(),
+                    &user_id,
+                    &matter_id,
+                    &input.report_path,
+                    &input.subject,
+                    &input.recipient,
+                    &input.created_by,
+                ],
+            )
+            .await?;
+        row_to_client_status_report(&row)
+    }
+
+    async fn list_client_status_reports(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<Vec<ClientStatusReportRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {CLIENT_STATUS_REPORT_COLUMNS} FROM client_status_reports \
+                     WHERE user_id = $1 AND matter_id = $2 \
+                     ORDER BY created_at DESC, report_path DESC"
+                ),
+                &[&user_id, &matter_id],
+            )
+            .await?;
+        rows.iter().map(row_to_client_status_report).collect()
+    }
+
+    async fn get_client_status_report(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        report_id: Uuid,
+    ) -> Result<Option<ClientStatusReportRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "SELECT {CLIENT_STATUS_REPORT_COLUMNS} FROM client_status_reports \
+                     WHERE user_id = $1 AND matter_id = $2 AND id = $3"
+                ),
+                &[&user_id, &matter_id, &report_id],
+            )
+            .await?;
+        row.map(|row| row_to_client_status_report(&row)).transpose()
+    }
+
+    async fn update_client_status_report(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        report_id: Uuid,
+        input: &UpdateClientStatusReportParams,
+    ) -> Result<Option<ClientStatusReportRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let reviewed = matches!(
+            input.status,
+            ClientStatusReportStatus::Approved | ClientStatusReportStatus::Rejected
+        );
+        let sent = input.status == ClientStatusReportStatus::Sent;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "UPDATE client_status_reports SET \
+                       status = $4, \
+                       reviewed_by = COALESCE($5, reviewed_by), \
+                       review_note = COALESCE($6, review_note), \
+                       recipient = COALESCE($7, recipient), \
+                       delivery_error = $8, \
+                       reviewed_at = CASE WHEN $9::boolean THEN NOW() ELSE reviewed_at END, \
+                       sent_at = CASE WHEN $10::boolean THEN NOW() ELSE sent_at END, \
+                       updated_at = NOW() \
+                     WHERE user_id = $1 AND matter_id = $2 AND id = $3 \
+                     RETURNING {CLIENT_STATUS_REPORT_COLUMNS}"
+                ),
+                &[
+                    &user_id,
+                    &matter_id,
+                    &report_id,
+                    &input.status.as_str(),
+                    &input.reviewed_by,
+                    &input.review_note,
+                    &input.recipient,
+                    &input.delivery_error,
+                    &reviewed,
+                    &sent,
+                ],
+            )
+            .await?;
+        row.map(|row| row_to_client_status_report(&row)).transpose()
+    }
+}
+
 #[async_trait::async_trait]
 impl TrustAccountingStore for PgBackend {
     async fn get_primary_trust_account(
@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::db::{LlmCacheEntry, LlmResponseCacheStore};
+use crate::error::DatabaseError;
+
+use super::PgBackend;
+
+#[async_trait]
+impl LlmResponseCacheStore for PgBackend {
+    async fn get_llm_cache_entry(
+        &self,
+        cache_key: &str,
+    ) -> Result<Option<LlmCacheEntry>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                "UPDATE llm_response_cache SET hit_count = hit_count + 1 \
+                 WHERE cache_key = $1 AND expires_at > NOW() \
+                 RETURNING cache_key, model, response, created_at, expires_at, hit_count",
+                &[&cache_key],
+            )
+            .await?;
+        Ok(row.map(|row| LlmCacheEntry {
+            cache_key: row.get("cache_key"),
+            model: row.get("model"),
+            response: row.get("response"),
+            created_at: row.get("created_at"),
+            expires_at: row.get("expires_at"),
+            hit_count: row.get("hit_count"),
+        }))
+    }
+
+    async fn put_llm_cache_entry(
+        &self,
+        cache_key: &str,
+        model: &str,
+        response: &serde_json::Value,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.store.conn().await?;
+        conn.execute(
+            "INSERT INTO llm_response_cache (cache_key, model, response, expires_at) \
+             VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (cache_key) DO UPDATE SET \
+               model = EXCLUDED.model, \
+               response = EXCLUDED.response, \
+               created_at = NOW(), \
+               expires_at = EXCLUDED.expires_at, \
+               hit_count = 0",
+            &[&cache_key, &model, response, &expires_at],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn purge_expired_llm_cache_entries(&self) -> Result<u64, DatabaseError> {
+        let conn = self.store.conn().await?;
+        Ok(conn
+            .execute(
+                "DELETE FROM llm_response_cache WHERE expires_at <= NOW()",
+                &[],
+            )
+            .await?)
+    }
+}
@@ -0,0 +1,114 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::{
+    CreateMatterExhibitParams, ExhibitStatus, MatterExhibitRecord, MatterExhibitStore,
+};
+use crate::error::DatabaseError;
+
+use super::PgBackend;
+
+const EXHIBIT_COLUMNS: &str = "id, user_id, matter_id, party_prefix, sequence, label, path, \
+     matter_document_id, description, status, created_by, created_at, withdrawn_at";
+
+fn row_to_exhibit(row: &tokio_postgres::Row) -> Result<MatterExhibitRecord, DatabaseError> {
+    let status_raw: String = row.get("status");
+    Ok(MatterExhibitRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        matter_id: row.get("matter_id"),
+        party_prefix: row.get("party_prefix"),
+        sequence: row.get("sequence"),
+        label: row.get("label"),
+        path: row.get("path"),
+        matter_document_id: row.get("matter_document_id"),
+        description: row.get("description"),
+        status: ExhibitStatus::from_db_value(&status_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("unknown exhibit status '{status_raw}'"))
+        })?,
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+        withdrawn_at: row.get("withdrawn_at"),
+    })
+}
+
+#[async_trait]
+impl MatterExhibitStore for PgBackend {
+    async fn create_matter_exhibit(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        input: &CreateMatterExhibitParams,
+    ) -> Result<MatterExhibitRecord, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                &format!(
+                    "INSERT INTO matter_exhibits \
+                     (id, user_id, matter_id, party_prefix, sequence, label, path, \
+                      matter_document_id, description, created_by) \
+                     SELECT $1, $2, $3, $4::TEXT, next.n, $4::TEXT || '-' || next.n::TEXT, \
+                            $5, $6, $7, $8 \
+                     FROM (SELECT COALESCE(MAX(sequence), 0) + 1 AS n FROM matter_exhibits \
+                           WHERE user_id = $2 AND matter_id = $3 AND party_prefix = $4::TEXT) next \
+                     RETURNING {EXHIBIT_COLUMNS}"
+                ),
+                &[
+                    &Uuid::new_v4(),
+                    &user_id,
+                    &matter_id,
+                    &input.party_prefix,
+                    &input.path,
+                    &input.matter_document_id,
+                    &input.description,
+                    &input.created_by,
+                ],
+            )
+            .await?;
+        row_to_exhibit(&row)
+    }
+
+    async fn list_matter_exhibits(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<Vec<MatterExhibitRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {EXHIBIT_COLUMNS} FROM matter_exhibits \
+                     WHERE user_id = $1 AND matter_id = $2 ORDER BY party_prefix, sequence"
+                ),
+                &[&user_id, &matter_id],
+            )
+            .await?;
+        rows.iter().map(row_to_exhibit).collect()
+    }
+
+    async fn withdraw_matter_exhibit(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        exhibit_id: Uuid,
+    ) -> Result<Option<MatterExhibitRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "UPDATE matter_exhibits SET status = $4, \
+                       withdrawn_at = COALESCE(withdrawn_at, NOW()) \
+                     WHERE user_id = $1 AND matter_id = $2 AND id = $3 \
+                     RETURNING {EXHIBIT_COLUMNS}"
+                ),
+                &[
+                    &user_id,
+                    &matter_id,
+                    &exhibit_id,
+                    &ExhibitStatus::Withdrawn.as_str(),
+                ],
+            )
+            .await?;
+        row.as_ref().map(row_to_exhibit).transpose()
+    }
+}
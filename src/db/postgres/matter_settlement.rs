@@ -0,0 +1,201 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::{
+    CreateSettlementAuthorityParams, CreateSettlementProposalParams, MatterSettlementStore,
+    SettlementAuthorityKind, SettlementAuthorityRecord, SettlementProposalKind,
+    SettlementProposalRecord, SettlementProposalStatus, SettlementSide,
+};
+use crate::error::DatabaseError;
+
+use super::PgBackend;
+
+const PROPOSAL_COLUMNS: &str = "id, user_id, matter_id, kind, side, from_party, to_party, amount, \
+     terms, proposed_on, expires_on, status, response_notes, created_by, created_at, updated_at";
+
+const AUTHORITY_COLUMNS: &str =
+    "id, user_id, matter_id, kind, amount, granted_on, granted_by, notes, created_by, created_at";
+
+fn row_to_proposal(row: &tokio_postgres::Row) -> Result<SettlementProposalRecord, DatabaseError> {
+    let kind_raw: String = row.get("kind");
+    let side_raw: String = row.get("side");
+    let status_raw: String = row.get("status");
+    Ok(SettlementProposalRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        matter_id: row.get("matter_id"),
+        kind: SettlementProposalKind::from_db_value(&kind_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("unknown settlement proposal kind '{kind_raw}'"))
+        })?,
+        side: SettlementSide::from_db_value(&side_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("unknown settlement side '{side_raw}'"))
+        })?,
+        from_party: row.get("from_party"),
+        to_party: row.get("to_party"),
+        amount: row.get("amount"),
+        terms: row.get("terms"),
+        proposed_on: row.get("proposed_on"),
+        expires_on: row.get("expires_on"),
+        status: SettlementProposalStatus::from_db_value(&status_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("unknown settlement status '{status_raw}'"))
+        })?,
+        response_notes: row.get("response_notes"),
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
+fn row_to_authority(row: &tokio_postgres::Row) -> Result<SettlementAuthorityRecord, DatabaseError> {
+    let kind_raw: String = row.get("kind");
+    Ok(SettlementAuthorityRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        matter_id: row.get("matter_id"),
+        kind: SettlementAuthorityKind::from_db_value(&kind_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("unknown settlement authority kind '{kind_raw}'"))
+        })?,
+        amount: row.get("amount"),
+        granted_on: row.get("granted_on"),
+        granted_by: row.get("granted_by"),
+        notes: row.get("notes"),
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+    })
+}
+
+#[async_trait]
+impl MatterSettlementStore for PgBackend {
+    async fn create_settlement_proposal(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        input: &CreateSettlementProposalParams,
+    ) -> Result<SettlementProposalRecord, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                &format!(
+                    "INSERT INTO matter_settlement_proposals \
+                     (id, user_id, matter_id, kind, side, from_party, to_party, amount, terms, \
+                      proposed_on, expires_on, created_by) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) \
+                     RETURNING {PROPOSAL_COLUMNS}"
+                ),
+                &[
+                    &Uuid::new_v4(),
+                    &user_id,
+                    &matter_id,
+                    &input.kind.as_str(),
+                    &input.side.as_str(),
+                    &input.from_party,
+                    &input.to_party,
+                    &input.amount,
+                    &input.terms,
+                    &input.proposed_on,
+                    &input.expires_on,
+                    &input.created_by,
+                ],
+            )
+            .await?;
+        row_to_proposal(&row)
+    }
+
+    async fn list_settlement_proposals(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<Vec<SettlementProposalRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {PROPOSAL_COLUMNS} FROM matter_settlement_proposals \
+                     WHERE user_id = $1 AND matter_id = $2 ORDER BY proposed_on, created_at"
+                ),
+                &[&user_id, &matter_id],
+            )
+            .await?;
+        rows.iter().map(row_to_proposal).collect()
+    }
+
+    async fn update_settlement_proposal_status(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        proposal_id: Uuid,
+        status: SettlementProposalStatus,
+        response_notes: Option<&str>,
+    ) -> Result<Option<SettlementProposalRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "UPDATE matter_settlement_proposals SET status = $4, \
+                       response_notes = COALESCE($5, response_notes), updated_at = NOW() \
+                     WHERE user_id = $1 AND matter_id = $2 AND id = $3 \
+                     RETURNING {PROPOSAL_COLUMNS}"
+                ),
+                &[
+                    &user_id,
+                    &matter_id,
+                    &proposal_id,
+                    &status.as_str(),
+                    &response_notes,
+                ],
+            )
+            .await?;
+        row.as_ref().map(row_to_proposal).transpose()
+    }
+
+    async fn create_settlement_authority(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        input: &CreateSettlementAuthorityParams,
+    ) -> Result<SettlementAuthorityRecord, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                &format!(
+                    "INSERT INTO matter_settlement_authority \
+                     (id, user_id, matter_id, kind, amount, granted_on, granted_by, notes, \
+                      created_by) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) \
+                     RETURNING {AUTHORITY_COLUMNS}"
+                ),
+                &[
+                    &Uuid::new_v4(),
+                    &user_id,
+                    &matter_id,
+                    &input.kind.as_str(),
+                    &input.amount,
+                    &input.granted_on,
+                    &input.granted_by,
+                    &input.notes,
+                    &input.created_by,
+                ],
+            )
+            .await?;
+        row_to_authority(&row)
+    }
+
+    async fn list_settlement_authority(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<Vec<SettlementAuthorityRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {AUTHORITY_COLUMNS} FROM matter_settlement_authority \
+                     WHERE user_id = $1 AND matter_id = $2 \
+                     ORDER BY granted_on DESC, created_at DESC"
+                ),
+                &[&user_id, &matter_id],
+            )
+            .await?;
+        rows.iter().map(row_to_authority).collect()
+    }
+}
@@ -0,0 +1,201 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::db::{
+    AuditSeverity, NewNotificationParams, NotificationFilter, NotificationKind, NotificationRecord,
+    NotificationStore,
+};
+use crate::error::DatabaseError;
+
+use super::PgBackend;
+
+const NOTIFICATION_COLUMNS: &str = "id, user_id, kind, severity, title, body, matter_id, \
+     source, read_at, acked_at, snoozed_until, created_at";
+
+fn row_to_notification(row: &tokio_postgres::Row) -> Result<NotificationRecord, DatabaseError> {
+    let kind_raw: String = row.get("kind");
+    let kind = NotificationKind::from_db_value(&kind_raw).ok_or_else(|| {
+        DatabaseError::Serialization(format!("invalid notification kind '{kind_raw}'"))
+    })?;
+    let severity: String = row.get("severity");
+    Ok(NotificationRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        kind,
+        severity: AuditSeverity::from_db_value(&severity).unwrap_or(AuditSeverity::Info),
+        title: row.get("title"),
+        body: row.get("body"),
+        matter_id: row.get("matter_id"),
+        source: row.get("source"),
+        read_at: row.get("read_at"),
+        acked_at: row.get("acked_at"),
+        snoozed_until: row.get("snoozed_until"),
+        created_at: row.get("created_at"),
+    })
+}
+
+/// `WHERE` condition for `filter`.
+fn filter_condition(filter: NotificationFilter) -> &'static str {
+    match filter {
+        NotificationFilter::Active => {
+            "acked_at IS NULL AND (snoozed_until IS NULL OR snoozed_until <= NOW())"
+        }
+        NotificationFilter::Unread => {
+            "read_at IS NULL AND acked_at IS NULL \
+             AND (snoozed_until IS NULL OR snoozed_until <= NOW())"
+        }
+        NotificationFilter::Snoozed => "acked_at IS NULL AND snoozed_until > NOW()",
+        NotificationFilter::Acked => "acked_at IS NOT NULL",
+        NotificationFilter::All => "TRUE",
+    }
+}
+
+#[async_trait]
+impl NotificationStore for PgBackend {
+    async fn create_notification(
+        &self,
+        params: &NewNotificationParams,
+    ) -> Result<NotificationRecord, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                &format!(
+                    "INSERT INTO notifications \
+                     (id, user_id, kind, severity, title, body, matter_id, source) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+                     RETURNING {NOTIFICATION_COLUMNS}"
+                ),
+                &[
+                    &Uuid::new_v4(),
+                    &params.user_id,
+                    &params.kind.as_str(),
+                    &params.severity.as_str(),
+                    &params.title,
+                    &params.body,
+                    &params.matter_id,
+                    &params.source,
+                ],
+            )
+            .await?;
+        row_to_notification(&row)
+    }
+
+    async fn list_notifications(
+        &self,
+        user_id: &str,
+        filter: NotificationFilter,
+        kind: Option<NotificationKind>,
+        limit: i64,
+    ) -> Result<Vec<NotificationRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {NOTIFICATION_COLUMNS} FROM notifications \
+                     WHERE user_id = $1 AND {} \
+                       AND ($2::text IS NULL OR kind = $2) \
+                     ORDER BY created_at DESC LIMIT $3",
+                    filter_condition(filter)
+                ),
+                &[&user_id, &kind.map(NotificationKind::as_str), &limit],
+            )
+            .await?;
+        rows.iter().map(row_to_notification).collect()
+    }
+
+    async fn count_unread_notifications(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<(NotificationKind, i64)>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT kind, COUNT(*) AS unread FROM notifications \
+                     WHERE user_id = $1 AND {} \
+                     GROUP BY kind ORDER BY kind",
+                    filter_condition(NotificationFilter::Unread)
+                ),
+                &[&user_id],
+            )
+            .await?;
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                let kind: String = row.get("kind");
+                NotificationKind::from_db_value(&kind).map(|kind| (kind, row.get("unread")))
+            })
+            .collect())
+    }
+
+    async fn mark_notification_read(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<NotificationRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "UPDATE notifications SET read_at = COALESCE(read_at, NOW()) \
+                     WHERE user_id = $1 AND id = $2 RETURNING {NOTIFICATION_COLUMNS}"
+                ),
+                &[&user_id, &id],
+            )
+            .await?;
+        row.as_ref().map(row_to_notification).transpose()
+    }
+
+    async fn mark_all_notifications_read(&self, user_id: &str) -> Result<u64, DatabaseError> {
+        let conn = self.store.conn().await?;
+        Ok(conn
+            .execute(
+                &format!(
+                    "UPDATE notifications SET read_at = NOW() WHERE user_id = $1 AND {}",
+                    filter_condition(NotificationFilter::Unread)
+                ),
+                &[&user_id],
+            )
+            .await?)
+    }
+
+    async fn ack_notification(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<NotificationRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "UPDATE notifications SET read_at = COALESCE(read_at, NOW()), \
+                       acked_at = COALESCE(acked_at, NOW()), snoozed_until = NULL \
+                     WHERE user_id = $1 AND id = $2 RETURNING {NOTIFICATION_COLUMNS}"
+                ),
+                &[&user_id, &id],
+            )
+            .await?;
+        row.as_ref().map(row_to_notification).transpose()
+    }
+
+    async fn snooze_notification(
+        &self,
+        user_id: &str,
+        id: Uuid,
+        until: DateTime<Utc>,
+    ) -> Result<Option<NotificationRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "UPDATE notifications SET snoozed_until = $3, read_at = NULL \
+                     WHERE user_id = $1 AND id = $2 AND acked_at IS NULL \
+                     RETURNING {NOTIFICATION_COLUMNS}"
+                ),
+                &[&user_id, &id, &until],
+            )
+            .await?;
+        row.as_ref().map(row_to_notification).transpose()
+    }
+}
@@ -0,0 +1,317 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::{
+    ClientRecord, OrgRole, OrganizationMatterRecord, OrganizationMemberRecord, OrganizationRecord,
+    OrganizationStore,
+};
+use crate::error::DatabaseError;
+
+use super::{PgBackend, row_to_client_record};
+
+const ORGANIZATION_COLUMNS: &str = "o.id, o.name, o.created_by, o.created_at, o.updated_at";
+
+const MEMBER_COLUMNS: &str = "org_id, user_id, role, created_at, updated_at";
+
+fn parse_org_role(raw: &str) -> Result<OrgRole, DatabaseError> {
+    OrgRole::from_db_value(raw)
+        .ok_or_else(|| DatabaseError::Serialization(format!("invalid organization role '{raw}'")))
+}
+
+fn row_to_organization(row: &tokio_postgres::Row) -> OrganizationRecord {
+    OrganizationRecord {
+        id: row.get("id"),
+        name: row.get("name"),
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+fn row_to_member(row: &tokio_postgres::Row) -> Result<OrganizationMemberRecord, DatabaseError> {
+    let role_raw: String = row.get("role");
+    Ok(OrganizationMemberRecord {
+        org_id: row.get("org_id"),
+        user_id: row.get("user_id"),
+        role: parse_org_role(&role_raw)?,
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
+/// Fail with [`DatabaseError::Constraint`] when `user_id` is the last owner
+/// of `org_id`. Locks the organization row first, so concurrent demotions
+/// and removals in the same organization run one at a time.
+async fn ensure_keeps_an_owner(
+    tx: &deadpool_postgres::Transaction<'_>,
+    org_id: Uuid,
+    user_id: &str,
+) -> Result<(), DatabaseError> {
+    tx.execute(
+        "SELECT id FROM organizations WHERE id = $1 FOR UPDATE",
+        &[&org_id],
+    )
+    .await?;
+    let last_owner = tx
+        .query_one(
+            "SELECT EXISTS (SELECT 1 FROM organization_members \
+             WHERE org_id = $1 AND user_id = $2 AND role = 'owner') \
+             AND NOT EXISTS (SELECT 1 FROM organization_members \
+             WHERE org_id = $1 AND role = 'owner' AND user_id <> $2)",
+            &[&org_id, &user_id],
+        )
+        .await?
+        .get::<_, bool>(0);
+    if last_owner {
+        return Err(DatabaseError::Constraint(
+            "organization must keep at least one owner".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl OrganizationStore for PgBackend {
+    async fn create_organization(
+        &self,
+        name: &str,
+        created_by: &str,
+    ) -> Result<OrganizationRecord, DatabaseError> {
+        let id = Uuid::new_v4();
+        let mut conn = self.store.conn().await?;
+        let tx = conn.transaction().await?;
+        let row = tx
+            .query_one(
+                "INSERT INTO organizations (id, name, created_by) VALUES ($1, $2, $3) \
+                 RETURNING id, name, created_by, created_at, updated_at",
+                &[&id, &name, &created_by],
+            )
+            .await?;
+        tx.execute(
+            "INSERT INTO organization_members (org_id, user_id, role) VALUES ($1, $2, $3)",
+            &[&id, &created_by, &OrgRole::Owner.as_str()],
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(row_to_organization(&row))
+    }
+
+    async fn get_organization(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Option<OrganizationRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                &format!("SELECT {ORGANIZATION_COLUMNS} FROM organizations o WHERE o.id = $1"),
+                &[&org_id],
+            )
+            .await?;
+        Ok(row.as_ref().map(row_to_organization))
+    }
+
+    async fn list_organizations_for_user(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<(OrganizationRecord, OrgRole)>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {ORGANIZATION_COLUMNS}, om.role FROM organizations o \
+                     JOIN organization_members om ON om.org_id = o.id \
+                     WHERE om.user_id = $1 ORDER BY o.name, o.id"
+                ),
+                &[&user_id],
+            )
+            .await?;
+        rows.iter()
+            .map(|row| {
+                let role_raw: String = row.get("role");
+                Ok((row_to_organization(row), parse_org_role(&role_raw)?))
+            })
+            .collect()
+    }
+
+    async fn upsert_organization_member(
+        &self,
+        org_id: Uuid,
+        user_id: &str,
+        role: OrgRole,
+    ) -> Result<OrganizationMemberRecord, DatabaseError> {
+        let mut conn = self.store.conn().await?;
+        let tx = conn.transaction().await?;
+        if role != OrgRole::Owner {
+            ensure_keeps_an_owner(&tx, org_id, user_id).await?;
+        }
+        let row = tx
+            .query_one(
+                &format!(
+                    "INSERT INTO organization_members (org_id, user_id, role) \
+                     VALUES ($1, $2, $3) \
+                     ON CONFLICT (org_id, user_id) DO UPDATE SET \
+                     role = EXCLUDED.role, updated_at = NOW() \
+                     RETURNING {MEMBER_COLUMNS}"
+                ),
+                &[&org_id, &user_id, &role.as_str()],
+            )
+            .await?;
+        tx.commit().await?;
+        row_to_member(&row)
+    }
+
+    async fn list_organization_members(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Vec<OrganizationMemberRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {MEMBER_COLUMNS} FROM organization_members \
+                     WHERE org_id = $1 ORDER BY user_id"
+                ),
+                &[&org_id],
+            )
+            .await?;
+        rows.iter().map(row_to_member).collect()
+    }
+
+    async fn get_organization_role(
+        &self,
+        org_id: Uuid,
+        user_id: &str,
+    ) -> Result<Option<OrgRole>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                "SELECT role FROM organization_members WHERE org_id = $1 AND user_id = $2",
+                &[&org_id, &user_id],
+            )
+            .await?;
+        row.map(|row| parse_org_role(&row.get::<_, String>("role")))
+            .transpose()
+    }
+
+    async fn remove_organization_member(
+        &self,
+        org_id: Uuid,
+        user_id: &str,
+    ) -> Result<bool, DatabaseError> {
+        let mut conn = self.store.conn().await?;
+        let tx = conn.transaction().await?;
+        ensure_keeps_an_owner(&tx, org_id, user_id).await?;
+        let removed = tx
+            .execute(
+                "DELETE FROM organization_members WHERE org_id = $1 AND user_id = $2",
+                &[&org_id, &user_id],
+            )
+            .await?;
+        tx.commit().await?;
+        Ok(removed > 0)
+    }
+
+    async fn set_matter_organization(
+        &self,
+        matter_owner_user_id: &str,
+        matter_id: &str,
+        org_id: Option<Uuid>,
+    ) -> Result<bool, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let updated = conn
+            .execute(
+                "UPDATE matters SET org_id = $3, updated_at = NOW() \
+                 WHERE user_id = $1 AND matter_id = $2",
+                &[&matter_owner_user_id, &matter_id, &org_id],
+            )
+            .await?;
+        Ok(updated > 0)
+    }
+
+    async fn get_matter_organization(
+        &self,
+        matter_owner_user_id: &str,
+        matter_id: &str,
+    ) -> Result<Option<Uuid>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                "SELECT org_id FROM matters WHERE user_id = $1 AND matter_id = $2",
+                &[&matter_owner_user_id, &matter_id],
+            )
+            .await?;
+        Ok(row.and_then(|row| row.get::<_, Option<Uuid>>("org_id")))
+    }
+
+    async fn set_client_organization(
+        &self,
+        user_id: &str,
+        client_id: Uuid,
+        org_id: Option<Uuid>,
+    ) -> Result<bool, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let updated = conn
+            .execute(
+                "UPDATE clients SET org_id = $3, updated_at = NOW() \
+                 WHERE user_id = $1 AND id = $2",
+                &[&user_id, &client_id, &org_id],
+            )
+            .await?;
+        Ok(updated > 0)
+    }
+
+    async fn set_documents_organization(
+        &self,
+        user_id: &str,
+        path_prefix: &str,
+        org_id: Option<Uuid>,
+    ) -> Result<u64, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let updated = conn
+            .execute(
+                "UPDATE memory_documents SET org_id = $3 \
+                 WHERE user_id = $1 AND left(path, length($2)) = $2",
+                &[&user_id, &path_prefix, &org_id],
+            )
+            .await?;
+        Ok(updated)
+    }
+
+    async fn list_organization_matters(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Vec<OrganizationMatterRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                "SELECT user_id, matter_id FROM matters WHERE org_id = $1 \
+                 ORDER BY matter_id, user_id",
+                &[&org_id],
+            )
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|row| OrganizationMatterRecord {
+                org_id,
+                matter_owner_user_id: row.get("user_id"),
+                matter_id: row.get("matter_id"),
+            })
+            .collect())
+    }
+
+    async fn list_organization_clients(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Vec<ClientRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                "SELECT id, user_id, name, name_normalized, client_type, email, phone, address, \
+                 notes, created_at, updated_at \
+                 FROM clients WHERE org_id = $1 ORDER BY name_normalized, id",
+                &[&org_id],
+            )
+            .await?;
+        rows.iter().map(row_to_client_record).collect()
+    }
+}
@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::{
+    NewWatchlistEntryParams, PartyWatchlistStore, WatchlistCategory, WatchlistEntryRecord,
+    normalize_party_name,
+};
+use crate::error::DatabaseError;
+
+use super::PgBackend;
+
+const WATCHLIST_COLUMNS: &str =
+    "id, user_id, name, name_normalized, category, reason, created_by, created_at";
+
+fn row_to_watchlist_entry(
+    row: &tokio_postgres::Row,
+) -> Result<WatchlistEntryRecord, DatabaseError> {
+    let category_raw: String = row.get("category");
+    Ok(WatchlistEntryRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        name: row.get("name"),
+        name_normalized: row.get("name_normalized"),
+        category: WatchlistCategory::from_db_value(&category_raw).ok_or_else(|| {
+            DatabaseError::Serialization(format!("unknown watchlist category '{category_raw}'"))
+        })?,
+        reason: row.get("reason"),
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+    })
+}
+
+#[async_trait]
+impl PartyWatchlistStore for PgBackend {
+    async fn upsert_watchlist_entry(
+        &self,
+        params: &NewWatchlistEntryParams,
+    ) -> Result<WatchlistEntryRecord, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                &format!(
+                    "INSERT INTO party_watchlist \
+                     (id, user_id, name, name_normalized, category, reason, created_by) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7) \
+                     ON CONFLICT (user_id, name_normalized) DO UPDATE SET \
+                       name = EXCLUDED.name, category = EXCLUDED.category, \
+                       reason = EXCLUDED.reason \
+                     RETURNING {WATCHLIST_COLUMNS}"
+                ),
+                &[
+                    &Uuid::new_v4(),
+                    &params.user_id,
+                    &params.name,
+                    &normalize_party_name(&params.name),
+                    &params.category.as_str(),
+                    &params.reason,
+                    &params.created_by,
+                ],
+            )
+            .await?;
+        row_to_watchlist_entry(&row)
+    }
+
+    async fn list_watchlist_entries(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<WatchlistEntryRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {WATCHLIST_COLUMNS} FROM party_watchlist \
+                     WHERE user_id = $1 ORDER BY name_normalized"
+                ),
+                &[&user_id],
+            )
+            .await?;
+        rows.iter().map(row_to_watchlist_entry).collect()
+    }
+
+    async fn delete_watchlist_entry(&self, user_id: &str, id: Uuid) -> Result<bool, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let deleted = conn
+            .execute(
+                "DELETE FROM party_watchlist WHERE user_id = $1 AND id = $2",
+                &[&user_id, &id],
+            )
+            .await?;
+        Ok(deleted > 0)
+    }
+}
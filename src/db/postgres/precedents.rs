@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::db::{CreatePrecedentParams, PrecedentRecord, PrecedentStore};
+use crate::error::DatabaseError;
+
+use super::PgBackend;
+
+const PRECEDENT_COLUMNS: &str = "id, user_id, title, path, source_matter_id, source_path, \
+     document_type, practice_area, jurisdiction, promoted_by, created_at";
+
+fn row_to_precedent(row: &tokio_postgres::Row) -> PrecedentRecord {
+    PrecedentRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        title: row.get("title"),
+        path: row.get("path"),
+        source_matter_id: row.get("source_matter_id"),
+        source_path: row.get("source_path"),
+        document_type: row.get("document_type"),
+        practice_area: row.get("practice_area"),
+        jurisdiction: row.get("jurisdiction"),
+        promoted_by: row.get("promoted_by"),
+        created_at: row.get("created_at"),
+    }
+}
+
+#[async_trait]
+impl PrecedentStore for PgBackend {
+    async fn create_precedent(
+        &self,
+        user_id: &str,
+        input: &CreatePrecedentParams,
+    ) -> Result<PrecedentRecord, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                &format!(
+                    "INSERT INTO precedents \
+                     (id, user_id, title, path, source_matter_id, source_path, \
+                      document_type, practice_area, jurisdiction, promoted_by) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) \
+                     RETURNING {PRECEDENT_COLUMNS}"
+                ),
+                &[
+                    &Uuid::new_v4(),
+                    &user_id,
+                    &input.title,
+                    &input.path,
+                    &input.source_matter_id,
+                    &input.source_path,
+                    &input.document_type,
+                    &input.practice_area,
+                    &input.jurisdiction,
+                    &input.promoted_by,
+                ],
+            )
+            .await?;
+        Ok(row_to_precedent(&row))
+    }
+
+    async fn list_precedents(&self, user_id: &str) -> Result<Vec<PrecedentRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {PRECEDENT_COLUMNS} FROM precedents \
+                     WHERE user_id = $1 ORDER BY created_at DESC, title"
+                ),
+                &[&user_id],
+            )
+            .await?;
+        Ok(rows.iter().map(row_to_precedent).collect())
+    }
+}
@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+
+use crate::db::{SetSmsConsentParams, SmsConsentRecord, SmsConsentStatus, SmsConsentStore};
+use crate::error::DatabaseError;
+
+use super::PgBackend;
+
+const SMS_CONSENT_COLUMNS: &str =
+    "user_id, phone_number, client_id, status, source, created_at, updated_at";
+
+fn row_to_sms_consent(row: &tokio_postgres::Row) -> Result<SmsConsentRecord, DatabaseError> {
+    let status_raw: String = row.get("status");
+    let status = SmsConsentStatus::from_db_value(&status_raw).ok_or_else(|| {
+        DatabaseError::Serialization(format!("invalid SMS consent status '{status_raw}'"))
+    })?;
+    Ok(SmsConsentRecord {
+        user_id: row.get("user_id"),
+        phone_number: row.get("phone_number"),
+        client_id: row.get("client_id"),
+        status,
+        source: row.get("source"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
+#[async_trait]
+impl SmsConsentStore for PgBackend {
+    async fn get_sms_consent(
+        &self,
+        user_id: &str,
+        phone_number: &str,
+    ) -> Result<Option<SmsConsentRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_opt(
+                &format!(
+                    "SELECT {SMS_CONSENT_COLUMNS} FROM sms_consents \
+                     WHERE user_id = $1 AND phone_number = $2"
+                ),
+                &[&user_id, &phone_number],
+            )
+            .await?;
+        row.as_ref().map(row_to_sms_consent).transpose()
+    }
+
+    async fn set_sms_consent(
+        &self,
+        user_id: &str,
+        params: &SetSmsConsentParams,
+    ) -> Result<SmsConsentRecord, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                &format!(
+                    "INSERT INTO sms_consents (user_id, phone_number, client_id, status, source) \
+                     VALUES ($1, $2, $3, $4, $5) \
+                     ON CONFLICT (user_id, phone_number) DO UPDATE SET \
+                       client_id = EXCLUDED.client_id, \
+                       status = EXCLUDED.status, \
+                       source = EXCLUDED.source, \
+                       updated_at = NOW() \
+                     RETURNING {SMS_CONSENT_COLUMNS}"
+                ),
+                &[
+                    &user_id,
+                    &params.phone_number,
+                    &params.client_id,
+                    &params.status.as_str(),
+                    &params.source,
+                ],
+            )
+            .await?;
+        row_to_sms_consent(&row)
+    }
+
+    async fn list_sms_consents(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<SmsConsentRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {SMS_CONSENT_COLUMNS} FROM sms_consents \
+                     WHERE user_id = $1 ORDER BY updated_at DESC"
+                ),
+                &[&user_id],
+            )
+            .await?;
+        rows.iter().map(row_to_sms_consent).collect()
+    }
+}
@@ -0,0 +1,229 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::db::{
+    DocumentTemplateUsageRecord, DocumentTemplateVersionRecord, RecordDocumentTemplateUseParams,
+    SetTemplateLibraryEntryParams, TemplateLibraryEntryRecord, TemplateLibraryStore,
+};
+use crate::error::DatabaseError;
+
+use super::PgBackend;
+
+const LIBRARY_ENTRY_COLUMNS: &str = "template_id, user_id, practice_area, jurisdiction, \
+     description, promoted_from, updated_at";
+
+const TEMPLATE_VERSION_COLUMNS: &str = "id, template_id, version, name, body, variables_json, \
+     created_by, created_at";
+
+fn row_to_library_entry(row: &tokio_postgres::Row) -> TemplateLibraryEntryRecord {
+    TemplateLibraryEntryRecord {
+        template_id: row.get("template_id"),
+        user_id: row.get("user_id"),
+        practice_area: row.get("practice_area"),
+        jurisdiction: row.get("jurisdiction"),
+        description: row.get("description"),
+        promoted_from: row.get("promoted_from"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+fn row_to_template_version(row: &tokio_postgres::Row) -> DocumentTemplateVersionRecord {
+    DocumentTemplateVersionRecord {
+        id: row.get("id"),
+        template_id: row.get("template_id"),
+        version: row.get("version"),
+        name: row.get("name"),
+        body: row.get("body"),
+        variables_json: row.get("variables_json"),
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+    }
+}
+
+#[async_trait]
+impl TemplateLibraryStore for PgBackend {
+    async fn set_template_library_entry(
+        &self,
+        user_id: &str,
+        template_id: Uuid,
+        input: &SetTemplateLibraryEntryParams,
+    ) -> Result<TemplateLibraryEntryRecord, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let row = conn
+            .query_one(
+                &format!(
+                    "INSERT INTO document_template_library \
+                     (template_id, user_id, practice_area, jurisdiction, description, promoted_from) \
+                     VALUES ($1, $2, $3, $4, $5, $6) \
+                     ON CONFLICT (template_id) DO UPDATE SET \
+                     practice_area = EXCLUDED.practice_area, \
+                     jurisdiction = EXCLUDED.jurisdiction, \
+                     description = EXCLUDED.description, \
+                     promoted_from = COALESCE(EXCLUDED.promoted_from, \
+                         document_template_library.promoted_from), \
+                     updated_at = NOW() \
+                     RETURNING {LIBRARY_ENTRY_COLUMNS}"
+                ),
+                &[
+                    &template_id,
+                    &user_id,
+                    &input.practice_area,
+                    &input.jurisdiction,
+                    &input.description,
+                    &input.promoted_from,
+                ],
+            )
+            .await?;
+        Ok(row_to_library_entry(&row))
+    }
+
+    async fn list_template_library_entries(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<TemplateLibraryEntryRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {LIBRARY_ENTRY_COLUMNS} FROM document_template_library \
+                     WHERE user_id = $1"
+                ),
+                &[&user_id],
+            )
+            .await?;
+        Ok(rows.iter().map(row_to_library_entry).collect())
+    }
+
+    async fn record_document_template_version(
+        &self,
+        user_id: &str,
+        template_id: Uuid,
+        created_by: &str,
+    ) -> Result<Option<DocumentTemplateVersionRecord>, DatabaseError> {
+        let mut conn = self.store.conn().await?;
+        let tx = conn.transaction().await?;
+        let Some(template) = tx
+            .query_opt(
+                "SELECT name, body, variables_json FROM document_templates \
+                 WHERE user_id = $1 AND id = $2 FOR UPDATE",
+                &[&user_id, &template_id],
+            )
+            .await?
+        else {
+            return Ok(None);
+        };
+        let name: String = template.get("name");
+        let body: String = template.get("body");
+        let variables_json: serde_json::Value = template.get("variables_json");
+
+        let latest = tx
+            .query_opt(
+                &format!(
+                    "SELECT {TEMPLATE_VERSION_COLUMNS} FROM document_template_versions \
+                     WHERE template_id = $1 ORDER BY version DESC LIMIT 1"
+                ),
+                &[&template_id],
+            )
+            .await?
+            .map(|row| row_to_template_version(&row));
+        if latest.as_ref().is_some_and(|latest| {
+            latest.name == name && latest.body == body && latest.variables_json == variables_json
+        }) {
+            return Ok(None);
+        }
+
+        let version = latest.map_or(1, |latest| latest.version + 1);
+        let row = tx
+            .query_one(
+                &format!(
+                    "INSERT INTO document_template_versions \
+                     (id, template_id, user_id, version, name, body, variables_json, created_by) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+                     RETURNING {TEMPLATE_VERSION_COLUMNS}"
+                ),
+                &[
+                    &Uuid::new_v4(),
+                    &template_id,
+                    &user_id,
+                    &version,
+                    &name,
+                    &body,
+                    &variables_json,
+                    &created_by,
+                ],
+            )
+            .await?;
+        tx.commit().await?;
+        Ok(Some(row_to_template_version(&row)))
+    }
+
+    async fn list_document_template_versions(
+        &self,
+        user_id: &str,
+        template_id: Uuid,
+    ) -> Result<Vec<DocumentTemplateVersionRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                &format!(
+                    "SELECT {TEMPLATE_VERSION_COLUMNS} FROM document_template_versions \
+                     WHERE user_id = $1 AND template_id = $2 ORDER BY version DESC"
+                ),
+                &[&user_id, &template_id],
+            )
+            .await?;
+        Ok(rows.iter().map(row_to_template_version).collect())
+    }
+
+    async fn record_document_template_use(
+        &self,
+        user_id: &str,
+        input: &RecordDocumentTemplateUseParams,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.store.conn().await?;
+        conn.execute(
+            "INSERT INTO document_template_uses \
+             (id, user_id, template_id, matter_id, document_path, used_by) \
+             VALUES ($1, $2, $3, $4, $5, $6)",
+            &[
+                &Uuid::new_v4(),
+                &user_id,
+                &input.template_id,
+                &input.matter_id,
+                &input.document_path,
+                &input.used_by,
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn document_template_usage(
+        &self,
+        user_id: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<DocumentTemplateUsageRecord>, DatabaseError> {
+        let conn = self.store.conn().await?;
+        let rows = conn
+            .query(
+                "SELECT template_id, COUNT(*) AS uses, COUNT(DISTINCT matter_id) AS matters, \
+                 MAX(used_at) AS last_used_at \
+                 FROM document_template_uses \
+                 WHERE user_id = $1 AND ($2::timestamptz IS NULL OR used_at >= $2) \
+                 GROUP BY template_id \
+                 ORDER BY uses DESC, last_used_at DESC",
+                &[&user_id, &since],
+            )
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|row| DocumentTemplateUsageRecord {
+                template_id: row.get("template_id"),
+                uses: row.get("uses"),
+                matters: row.get("matters"),
+                last_used_at: row.get("last_used_at"),
+            })
+            .collect())
+    }
+}
@@ -81,6 +81,9 @@ pub enum DatabaseError {
     #[error("Query failed: {0}")]
     Query(String),
 
+    #[error("Database unavailable: {0}")]
+    Unavailable(String),
+
     #[error("Entity not found: {entity} with id {id}")]
     NotFound { entity: String, id: String },
 
@@ -331,6 +334,13 @@ pub enum WorkspaceError {
 
     #[error("Heartbeat error: {reason}")]
     HeartbeatError { reason: String },
+
+    #[error("Document already exists: {path}")]
+    AlreadyExists { path: String },
+
+    /// An `If-Match` write found the document changed since it was read.
+    #[error("Document changed since it was read (current ETag {current_etag:?})")]
+    WriteConflict { current_etag: Option<String> },
 }
 
 /// Orchestrator errors (internal API, container management).
@@ -350,6 +360,9 @@ pub enum OrchestratorError {
 
     #[error("Docker error: {reason}")]
     Docker { reason: String },
+
+    #[error("Script run {job_id} timed out after {timeout:?}")]
+    ScriptTimeout { job_id: Uuid, timeout: Duration },
 }
 
 /// Worker errors (container-side execution).
@@ -401,6 +414,15 @@ pub enum RoutineError {
     #[error("Routine {name} at max concurrent runs")]
     MaxConcurrent { name: String },
 
+    #[error("Routine {name} skipped: {reason}")]
+    Skipped { name: String, reason: String },
+
+    #[error("Routine {name} does not have a webhook trigger")]
+    NotWebhook { name: String },
+
+    #[error("Invalid routine chain: {reason}")]
+    InvalidChain { reason: String },
+
     #[error("Database error: {reason}")]
     Database { reason: String },
 
@@ -0,0 +1,274 @@
+//! Forecast corrections regressed from recorded estimation snapshots.
+//!
+//! [`EstimationLearner`](super::EstimationLearner) adapts in memory and is
+//! lost on restart. The forecaster is rebuilt from persisted snapshots instead:
+//! per category it fits `actual = intercept + slope * estimated` by least
+//! squares, separately for cost and time, and uses the fit to correct new
+//! plan estimates.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::db::EstimationSampleRecord;
+
+/// Snapshots needed in a category before its estimates are corrected.
+pub const MIN_FORECAST_SAMPLES: usize = 5;
+
+/// How many recent snapshots to fit a forecast from.
+pub const FORECAST_HISTORY_LIMIT: i64 = 500;
+
+/// Least-squares line mapping an estimate to the expected actual.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearFit {
+    pub slope: f64,
+    pub intercept: f64,
+    /// Coefficient of determination of the fit (0-1).
+    pub r_squared: f64,
+}
+
+impl LinearFit {
+    /// Fit `(estimated, actual)` points.
+    ///
+    /// When every estimate is the same there is no slope to learn, so the
+    /// fit falls back to scaling by the ratio of the means.
+    pub fn fit(points: &[(f64, f64)]) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let sxx: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        let sxy: f64 = points
+            .iter()
+            .map(|(x, y)| (x - mean_x) * (y - mean_y))
+            .sum();
+        let syy: f64 = points.iter().map(|(_, y)| (y - mean_y).powi(2)).sum();
+
+        let (slope, intercept) = if sxx > f64::EPSILON {
+            let slope = sxy / sxx;
+            (slope, mean_y - slope * mean_x)
+        } else if mean_x > f64::EPSILON {
+            (mean_y / mean_x, 0.0)
+        } else {
+            return None;
+        };
+        let r_squared = if sxx > f64::EPSILON && syy > f64::EPSILON {
+            ((sxy * sxy) / (sxx * syy)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        Some(Self {
+            slope,
+            intercept,
+            r_squared,
+        })
+    }
+
+    /// Expected actual for an estimate; never negative.
+    pub fn apply(&self, estimated: f64) -> f64 {
+        (self.intercept + self.slope * estimated).max(0.0)
+    }
+}
+
+/// How well one metric (cost or time) is estimated in a category.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricForecast {
+    /// `None` until the category has [`MIN_FORECAST_SAMPLES`] samples.
+    pub fit: Option<LinearFit>,
+    /// Mean absolute error of the raw estimates, relative to the actuals.
+    pub raw_error_rate: f64,
+    /// The same error after applying `fit`; equals `raw_error_rate` when
+    /// there is no fit.
+    pub corrected_error_rate: f64,
+}
+
+impl MetricForecast {
+    fn from_points(points: &[(f64, f64)]) -> Self {
+        let fit = if points.len() >= MIN_FORECAST_SAMPLES {
+            LinearFit::fit(points)
+        } else {
+            None
+        };
+        let raw_error_rate = mean_relative_error(points.iter().copied());
+        let corrected_error_rate = match fit {
+            Some(fit) => mean_relative_error(points.iter().map(|&(x, y)| (fit.apply(x), y))),
+            None => raw_error_rate,
+        };
+        Self {
+            fit,
+            raw_error_rate,
+            corrected_error_rate,
+        }
+    }
+
+    fn correct(&self, estimated: f64) -> f64 {
+        self.fit.map_or(estimated, |fit| fit.apply(estimated))
+    }
+}
+
+/// Mean of `|predicted - actual| / actual`, skipping zero actuals.
+fn mean_relative_error(pairs: impl Iterator<Item = (f64, f64)>) -> f64 {
+    let (sum, count) = pairs
+        .filter(|(_, actual)| *actual > f64::EPSILON)
+        .fold((0.0, 0usize), |(sum, count), (predicted, actual)| {
+            (sum + (predicted - actual).abs() / actual, count + 1)
+        });
+    if count == 0 { 0.0 } else { sum / count as f64 }
+}
+
+/// Fitted cost and time forecasts for one category.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryForecast {
+    pub category: String,
+    pub sample_count: usize,
+    pub cost: MetricForecast,
+    pub time: MetricForecast,
+}
+
+/// Per-category forecasts fitted from completed estimation snapshots.
+#[derive(Debug, Clone, Default)]
+pub struct EstimationForecaster {
+    categories: HashMap<String, CategoryForecast>,
+}
+
+impl EstimationForecaster {
+    /// Fit a forecast for every category present in `samples`.
+    pub fn fit(samples: &[EstimationSampleRecord]) -> Self {
+        let mut grouped: HashMap<&str, Vec<&EstimationSampleRecord>> = HashMap::new();
+        for sample in samples {
+            grouped.entry(&sample.category).or_default().push(sample);
+        }
+        let categories = grouped
+            .into_iter()
+            .map(|(category, samples)| {
+                let cost: Vec<(f64, f64)> = samples
+                    .iter()
+                    .map(|s| {
+                        (
+                            s.estimated_cost.to_f64().unwrap_or(0.0),
+                            s.actual_cost.to_f64().unwrap_or(0.0),
+                        )
+                    })
+                    .collect();
+                let time: Vec<(f64, f64)> = samples
+                    .iter()
+                    .map(|s| (s.estimated_time_secs as f64, s.actual_time_secs as f64))
+                    .collect();
+                let forecast = CategoryForecast {
+                    category: category.to_string(),
+                    sample_count: samples.len(),
+                    cost: MetricForecast::from_points(&cost),
+                    time: MetricForecast::from_points(&time),
+                };
+                (category.to_string(), forecast)
+            })
+            .collect();
+        Self { categories }
+    }
+
+    /// Forecast for a single category.
+    pub fn forecast(&self, category: &str) -> Option<&CategoryForecast> {
+        self.categories.get(category)
+    }
+
+    /// All category forecasts, sorted by category name.
+    pub fn forecasts(&self) -> Vec<&CategoryForecast> {
+        let mut forecasts: Vec<_> = self.categories.values().collect();
+        forecasts.sort_by(|a, b| a.category.cmp(&b.category));
+        forecasts
+    }
+
+    /// Correct a raw estimate using the category's fit. Categories without
+    /// enough history return the estimate unchanged.
+    pub fn correct(&self, category: &str, cost: Decimal, time: Duration) -> (Decimal, Duration) {
+        let Some(forecast) = self.categories.get(category) else {
+            return (cost, time);
+        };
+        let corrected_cost = match forecast.cost.fit {
+            Some(fit) => Decimal::try_from(fit.apply(cost.to_f64().unwrap_or(0.0)))
+                .map(|c| c.round_dp(6))
+                .unwrap_or(cost),
+            None => cost,
+        };
+        let corrected_time =
+            Duration::from_secs_f64(forecast.time.correct(time.as_secs_f64()).round());
+        (corrected_cost, corrected_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn sample(
+        category: &str,
+        est_cost: Decimal,
+        cost: Decimal,
+        est_secs: i32,
+        secs: i32,
+    ) -> EstimationSampleRecord {
+        EstimationSampleRecord {
+            category: category.to_string(),
+            estimated_cost: est_cost,
+            actual_cost: cost,
+            estimated_time_secs: est_secs,
+            actual_time_secs: secs,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn linear_fit_recovers_slope_and_intercept() {
+        let points: Vec<(f64, f64)> = (1..=10).map(|x| (x as f64, 2.0 * x as f64 + 1.0)).collect();
+        let fit = LinearFit::fit(&points).unwrap();
+        assert!((fit.slope - 2.0).abs() < 1e-9);
+        assert!((fit.intercept - 1.0).abs() < 1e-9);
+        assert!((fit.r_squared - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn constant_estimates_fall_back_to_ratio_of_means() {
+        let fit = LinearFit::fit(&[(10.0, 15.0), (10.0, 25.0)]).unwrap();
+        assert_eq!(fit.intercept, 0.0);
+        assert!((fit.apply(10.0) - 20.0).abs() < 1e-9);
+        assert!(LinearFit::fit(&[(0.0, 3.0)]).is_none());
+    }
+
+    #[test]
+    fn forecaster_corrects_systematic_underestimates() {
+        let samples: Vec<_> = (1..=8)
+            .map(|i| {
+                let est = Decimal::from(i);
+                sample("research", est, est * dec!(1.5), i * 60, i * 90)
+            })
+            .collect();
+        let forecaster = EstimationForecaster::fit(&samples);
+
+        let forecast = forecaster.forecast("research").unwrap();
+        assert_eq!(forecast.sample_count, 8);
+        assert!(forecast.cost.raw_error_rate > 0.3);
+        assert!(forecast.cost.corrected_error_rate < 1e-6);
+
+        let (cost, time) = forecaster.correct("research", dec!(4), Duration::from_secs(120));
+        assert_eq!(cost, dec!(6));
+        assert_eq!(time.as_secs(), 180);
+    }
+
+    #[test]
+    fn sparse_or_unknown_categories_are_not_corrected() {
+        let samples = vec![sample("drafting", dec!(1), dec!(3), 60, 180)];
+        let forecaster = EstimationForecaster::fit(&samples);
+        assert!(forecaster.forecast("drafting").unwrap().cost.fit.is_none());
+        for category in ["drafting", "unknown"] {
+            assert_eq!(
+                forecaster.correct(category, dec!(1), Duration::from_secs(60)),
+                (dec!(1), Duration::from_secs(60))
+            );
+        }
+    }
+}
@@ -6,11 +6,16 @@
 //! - Statistical models that improve over time
 
 mod cost;
+mod forecast;
 mod learner;
 mod time;
 mod value;
 
 pub use cost::CostEstimator;
+pub use forecast::{
+    CategoryForecast, EstimationForecaster, FORECAST_HISTORY_LIMIT, LinearFit,
+    MIN_FORECAST_SAMPLES, MetricForecast,
+};
 pub use learner::{EstimationLearner, LearningModel};
 pub use time::TimeEstimator;
 pub use value::ValueEstimator;
@@ -0,0 +1,280 @@
+//! Typed domain event bus.
+//!
+//! Handlers publish a [`DomainEvent`] once when something notable happens
+//! (a matter is opened, a conflict is hit, a job finishes). Every consumer —
+//! the SSE/WebSocket stream, outbound webhooks, the audit log, and
+//! event-triggered routines — subscribes to the bus instead of being called
+//! directly from each handler.
+//!
+//! The bus is a tokio broadcast channel, so a subscriber that falls more than
+//! [`EVENT_BUS_CAPACITY`] events behind skips the oldest ones. Anything that
+//! must never be lost (such as the `matter_created` audit row) is still
+//! written synchronously by the handler; subscribers cover best-effort fan-out.
+
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// Events buffered per subscriber before the slowest one starts lagging.
+pub const EVENT_BUS_CAPACITY: usize = 256;
+
+/// Something that happened inside the firm's instance.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DomainEvent {
+    MatterCreated {
+        matter_id: String,
+        client_id: String,
+        status: String,
+        practice_area: Option<String>,
+        jurisdiction: Option<String>,
+        opened_date: Option<String>,
+    },
+    DeadlineAdded {
+        matter_id: String,
+        deadline_id: Uuid,
+        title: String,
+        deadline_type: String,
+        due_at: DateTime<Utc>,
+        rule_ref: Option<String>,
+        /// User who added the deadline.
+        actor: String,
+    },
+    /// A conflict check matched. Carries counts only so party names never
+    /// leave the firm through a subscriber.
+    ConflictHit {
+        matter_id: Option<String>,
+        source: String,
+        hit_count: usize,
+    },
+    JobCompleted {
+        job_id: String,
+        status: String,
+    },
+    /// A draft was written into a matter from a template.
+    DocumentGenerated {
+        matter_id: String,
+        document_id: Option<Uuid>,
+        path: String,
+        template: String,
+        /// User who generated the draft.
+        actor: String,
+    },
+}
+
+impl DomainEvent {
+    /// Stable dotted name used on the wire (SSE, webhooks, routine patterns).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MatterCreated { .. } => "matter.created",
+            Self::DeadlineAdded { .. } => "deadline.added",
+            Self::ConflictHit { .. } => "conflict.hit",
+            Self::JobCompleted { .. } => "job.completed",
+            Self::DocumentGenerated { .. } => "document.generated",
+        }
+    }
+
+    /// The matter this event concerns, if any.
+    pub fn matter_id(&self) -> Option<&str> {
+        match self {
+            Self::MatterCreated { matter_id, .. }
+            | Self::DeadlineAdded { matter_id, .. }
+            | Self::DocumentGenerated { matter_id, .. } => Some(matter_id),
+            Self::ConflictHit { matter_id, .. } => matter_id.as_deref(),
+            Self::JobCompleted { .. } => None,
+        }
+    }
+
+    /// JSON body shared by every subscriber that serializes the event.
+    /// Actors are left out; only the audit log records who acted.
+    pub fn payload(&self) -> serde_json::Value {
+        match self {
+            Self::MatterCreated {
+                matter_id,
+                client_id,
+                status,
+                practice_area,
+                jurisdiction,
+                opened_date,
+            } => serde_json::json!({
+                "matter_id": matter_id,
+                "client_id": client_id,
+                "status": status,
+                "practice_area": practice_area,
+                "jurisdiction": jurisdiction,
+                "opened_date": opened_date,
+            }),
+            Self::DeadlineAdded {
+                matter_id,
+                deadline_id,
+                title,
+                deadline_type,
+                due_at,
+                rule_ref,
+                ..
+            } => serde_json::json!({
+                "matter_id": matter_id,
+                "deadline_id": deadline_id,
+                "title": title,
+                "deadline_type": deadline_type,
+                "due_at": due_at.to_rfc3339(),
+                "rule_ref": rule_ref,
+            }),
+            Self::ConflictHit {
+                matter_id,
+                source,
+                hit_count,
+            } => serde_json::json!({
+                "matter_id": matter_id,
+                "source": source,
+                "hit_count": hit_count,
+            }),
+            Self::JobCompleted { job_id, status } => serde_json::json!({
+                "job_id": job_id,
+                "status": status,
+            }),
+            Self::DocumentGenerated {
+                matter_id,
+                document_id,
+                path,
+                template,
+                ..
+            } => serde_json::json!({
+                "matter_id": matter_id,
+                "document_id": document_id,
+                "path": path,
+                "template": template,
+            }),
+        }
+    }
+}
+
+/// Cloneable handle to the process-wide event channel.
+#[derive(Clone)]
+pub struct EventBus {
+    tx: broadcast::Sender<DomainEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+        Self { tx }
+    }
+
+    /// Publish an event. Never blocks; with no subscribers it is dropped.
+    pub fn publish(&self, event: DomainEvent) {
+        tracing::debug!(event = event.name(), "Domain event published");
+        let _ = self.tx.send(event);
+    }
+
+    /// Raw receiver for consumers that drive their own loop.
+    pub fn subscribe(&self) -> broadcast::Receiver<DomainEvent> {
+        self.tx.subscribe()
+    }
+
+    /// Run `handler` for every event until the bus is dropped. Lag is logged
+    /// and skipped rather than ending the subscription.
+    pub fn spawn_subscriber<F, Fut>(&self, name: &'static str, mut handler: F) -> JoinHandle<()>
+    where
+        F: FnMut(DomainEvent) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let mut rx = self.subscribe();
+        tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => handler(event).await,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(
+                            subscriber = name,
+                            skipped,
+                            "Domain event subscriber lagged; events dropped"
+                        );
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBus")
+            .field("subscribers", &self.tx.receiver_count())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    fn job_done(id: &str) -> DomainEvent {
+        DomainEvent::JobCompleted {
+            job_id: id.to_string(),
+            status: "completed".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn every_subscriber_sees_each_event() {
+        let bus = EventBus::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut handles = Vec::new();
+        for _ in 0..2 {
+            let seen = Arc::clone(&seen);
+            handles.push(bus.spawn_subscriber("test", move |event| {
+                let seen = Arc::clone(&seen);
+                async move { seen.lock().await.push(event.name()) }
+            }));
+        }
+
+        bus.publish(job_done("job-1"));
+        drop(bus);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        assert_eq!(*seen.lock().await, vec!["job.completed", "job.completed"]);
+    }
+
+    #[tokio::test]
+    async fn lagging_subscriber_keeps_running() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+        for i in 0..EVENT_BUS_CAPACITY + 5 {
+            bus.publish(job_done(&format!("job-{i}")));
+        }
+        assert!(matches!(
+            rx.recv().await,
+            Err(broadcast::error::RecvError::Lagged(5))
+        ));
+        assert_eq!(rx.recv().await.unwrap(), job_done("job-5"));
+    }
+
+    #[test]
+    fn conflict_payload_carries_counts_only() {
+        let event = DomainEvent::ConflictHit {
+            matter_id: Some("acme-v-doe".to_string()),
+            source: "manual_text_check".to_string(),
+            hit_count: 2,
+        };
+        assert_eq!(event.name(), "conflict.hit");
+        assert_eq!(event.matter_id(), Some("acme-v-doe"));
+        let payload = event.payload();
+        assert_eq!(payload["hit_count"], 2);
+        assert_eq!(payload.as_object().unwrap().len(), 3);
+    }
+}
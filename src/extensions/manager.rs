@@ -293,7 +293,8 @@ impl ExtensionManager {
                             name: server.name.clone(),
                             kind: ExtensionKind::McpServer,
                             description: server.description.clone(),
-                            url: Some(server.url.clone()),
+                            // Stdio servers have a command instead of a URL.
+                            url: (!server.is_stdio()).then(|| server.url.clone()),
                             authenticated,
                             active,
                             tools,
@@ -419,8 +420,10 @@ impl ExtensionManager {
                     self.tool_registry.unregister(tool_name).await;
                 }
 
-                // Remove MCP client
-                self.mcp_clients.write().await.remove(name);
+                // Remove MCP client, stopping its process if it was spawned
+                if let Some(client) = self.mcp_clients.write().await.remove(name) {
+                    client.shutdown().await;
+                }
 
                 // Remove from config
                 self.remove_mcp_server(name)
@@ -1557,7 +1560,11 @@ impl ExtensionManager {
 
         let has_tokens = is_authenticated(&server, &self.secrets, &self.user_id).await;
 
-        let client = if has_tokens || server.requires_auth() {
+        let client = if server.is_stdio() {
+            McpClient::connect_stdio(server.clone())
+                .await
+                .map_err(|e| ExtensionError::ActivationFailed(e.to_string()))?
+        } else if has_tokens || server.requires_auth() {
             McpClient::new_authenticated(
                 server.clone(),
                 Arc::clone(&self.mcp_session_manager),
@@ -1569,10 +1576,13 @@ impl ExtensionManager {
         };
 
         // Try to list and create tools
-        let mcp_tools = client
-            .list_tools()
-            .await
-            .map_err(|e| ExtensionError::ActivationFailed(e.to_string()))?;
+        let mcp_tools = match client.list_tools().await {
+            Ok(tools) => tools,
+            Err(e) => {
+                client.shutdown().await;
+                return Err(ExtensionError::ActivationFailed(e.to_string()));
+            }
+        };
 
         let tool_impls = client
             .create_tools()
@@ -1745,8 +1755,12 @@ impl ExtensionManager {
             None
         };
 
-        let loader =
+        let mut loader =
             WasmChannelLoader::new(Arc::clone(&channel_runtime), Arc::clone(&pairing_store));
+        if let Some(ref store) = self.store {
+            let settings: Arc<dyn crate::db::SettingsStore> = store.clone();
+            loader = loader.with_settings_store(settings, self.user_id.clone());
+        }
         let loaded = loader
             .load_from_files(name, &wasm_path, cap_path_option)
             .await
@@ -1755,6 +1769,10 @@ impl ExtensionManager {
         let channel_name = loaded.name().to_string();
         let webhook_secret_name = loaded.webhook_secret_name();
         let secret_header = loaded.webhook_secret_header().map(|s| s.to_string());
+        let verify_token_param = loaded.webhook_verify_token_param().map(|s| s.to_string());
+        let signature = loaded
+            .webhook_signature()
+            .map(|(header, name)| (header.to_string(), name.to_string()));
 
         // Get webhook secret from secrets store
         let webhook_secret = self
@@ -1819,6 +1837,21 @@ impl ExtensionManager {
                     secret_header,
                 )
                 .await;
+            if let Some(param) = verify_token_param {
+                wasm_channel_router
+                    .set_verify_token_param(&channel_name, param)
+                    .await;
+            }
+            if let Some((header, secret_name)) = signature
+                && let Ok(secret) = self
+                    .secrets
+                    .get_decrypted(&self.user_id, &secret_name)
+                    .await
+            {
+                wasm_channel_router
+                    .set_signing_secret(&channel_name, header, secret.expose().to_string())
+                    .await;
+            }
             tracing::info!(channel = %channel_name, "Registered hot-activated channel with webhook router");
         }
 
@@ -1926,17 +1959,28 @@ impl ExtensionManager {
 
         // Also refresh the webhook secret in the router
         // Load capabilities file to get the correct secret name (may be overridden)
-        let webhook_secret_name = {
+        let cap_file = {
             let cap_path = self
                 .wasm_channels_dir
                 .join(format!("{}.capabilities.json", name));
             match tokio::fs::read(&cap_path).await {
-                Ok(bytes) => crate::channels::wasm::ChannelCapabilitiesFile::from_bytes(&bytes)
-                    .map(|f| f.webhook_secret_name())
-                    .unwrap_or_else(|_| format!("{}_webhook_secret", name)),
-                Err(_) => format!("{}_webhook_secret", name),
+                Ok(bytes) => {
+                    crate::channels::wasm::ChannelCapabilitiesFile::from_bytes(&bytes).ok()
+                }
+                Err(_) => None,
             }
         };
+        let webhook_secret_name = cap_file
+            .as_ref()
+            .map(|f| f.webhook_secret_name())
+            .unwrap_or_else(|| format!("{}_webhook_secret", name));
+        if let Some((header, secret_name)) = cap_file.as_ref().and_then(|f| f.webhook_signature())
+            && let Ok(secret) = self.secrets.get_decrypted(&self.user_id, secret_name).await
+        {
+            router
+                .set_signing_secret(name, header.to_string(), secret.expose().to_string())
+                .await;
+        }
         if let Ok(secret) = self
             .secrets
             .get_decrypted(&self.user_id, &webhook_secret_name)
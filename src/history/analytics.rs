@@ -34,7 +34,7 @@ pub struct ToolStats {
 impl Store {
     /// Get job statistics.
     pub async fn get_job_stats(&self) -> Result<JobStats, DatabaseError> {
-        let conn = self.conn().await?;
+        let conn = self.read_conn().await?;
 
         let row = conn
             .query_one(
@@ -77,7 +77,7 @@ impl Store {
 
     /// Get tool usage statistics.
     pub async fn get_tool_stats(&self) -> Result<Vec<ToolStats>, DatabaseError> {
-        let conn = self.conn().await?;
+        let conn = self.read_conn().await?;
 
         let rows = conn
             .query(
@@ -128,7 +128,7 @@ impl Store {
         &self,
         category: Option<&str>,
     ) -> Result<EstimationAccuracy, DatabaseError> {
-        let conn = self.conn().await?;
+        let conn = self.read_conn().await?;
 
         let query = if category.is_some() {
             r#"
@@ -169,7 +169,7 @@ impl Store {
         category: &str,
         limit: i64,
     ) -> Result<Vec<CategoryHistoryEntry>, DatabaseError> {
-        let conn = self.conn().await?;
+        let conn = self.read_conn().await?;
 
         let rows = conn
             .query(
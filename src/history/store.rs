@@ -2,10 +2,8 @@
 
 use chrono::{DateTime, Utc};
 #[cfg(feature = "postgres")]
-use deadpool_postgres::{Config, Pool, Runtime};
+use deadpool_postgres::Pool;
 use rust_decimal::Decimal;
-#[cfg(feature = "postgres")]
-use tokio_postgres::NoTls;
 use uuid::Uuid;
 
 #[cfg(feature = "postgres")]
@@ -13,6 +11,8 @@ use crate::config::DatabaseConfig;
 #[cfg(feature = "postgres")]
 use crate::context::{ActionRecord, JobContext, JobState};
 #[cfg(feature = "postgres")]
+use crate::db::postgres::failover::PgPools;
+#[cfg(feature = "postgres")]
 use crate::error::DatabaseError;
 
 /// Record for an LLM call to be persisted.
@@ -31,33 +31,26 @@ pub struct LlmCallRecord<'a> {
 /// Database store for the agent.
 #[cfg(feature = "postgres")]
 pub struct Store {
-    pool: Pool,
+    pools: PgPools,
 }
 
 #[cfg(feature = "postgres")]
 impl Store {
     /// Wrap an existing pool (useful when the caller already has a connection).
     pub fn from_pool(pool: Pool) -> Self {
-        Self { pool }
+        Self {
+            pools: PgPools::from_pool(pool),
+        }
     }
 
     /// Create a new store and connect to the database.
     pub async fn new(config: &DatabaseConfig) -> Result<Self, DatabaseError> {
-        let mut cfg = Config::new();
-        cfg.url = Some(config.url().to_string());
-        cfg.pool = Some(deadpool_postgres::PoolConfig {
-            max_size: config.pool_size,
-            ..Default::default()
-        });
-
-        let pool = cfg
-            .create_pool(Some(Runtime::Tokio1), NoTls)
-            .map_err(|e| DatabaseError::Pool(e.to_string()))?;
+        let pools = PgPools::from_config(config)?;
 
         // Test connection
-        let _ = pool.get().await?;
+        let _ = pools.conn().await?;
 
-        Ok(Self { pool })
+        Ok(Self { pools })
     }
 
     /// Run database migrations (embedded via refinery).
@@ -65,7 +58,7 @@ impl Store {
         use refinery::embed_migrations;
         embed_migrations!("migrations");
 
-        let mut client = self.pool.get().await?;
+        let mut client = self.conn().await?;
         migrations::runner()
             .run_async(&mut **client)
             .await
@@ -73,16 +66,27 @@ impl Store {
         Ok(())
     }
 
-    /// Get a connection from the pool.
+    /// Get a connection from the primary pool, retrying transient errors.
     pub async fn conn(&self) -> Result<deadpool_postgres::Object, DatabaseError> {
-        Ok(self.pool.get().await?)
+        self.pools.conn().await
+    }
+
+    /// Get a connection for a lag-tolerant read (search, listings,
+    /// reporting). Uses the read replica when configured and healthy.
+    pub async fn read_conn(&self) -> Result<deadpool_postgres::Object, DatabaseError> {
+        self.pools.read_conn().await
     }
 
     /// Get a clone of the database pool.
     ///
     /// Useful for sharing the pool with other components like Workspace.
     pub fn pool(&self) -> Pool {
-        self.pool.clone()
+        self.pools.primary().clone()
+    }
+
+    /// Primary and replica pools, for components that route their own reads.
+    pub fn pools(&self) -> &PgPools {
+        &self.pools
     }
 
     // ==================== Conversations ====================
@@ -453,6 +457,42 @@ impl Store {
 
         Ok(())
     }
+
+    /// List completed estimation snapshots, newest first.
+    pub async fn list_estimation_samples(
+        &self,
+        category: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<crate::db::EstimationSampleRecord>, DatabaseError> {
+        let conn = self.conn().await?;
+
+        let rows = conn
+            .query(
+                r#"
+                SELECT category, estimated_cost, actual_cost, estimated_time_secs,
+                       actual_time_secs, created_at
+                FROM estimation_snapshots
+                WHERE actual_cost IS NOT NULL AND actual_time_secs IS NOT NULL
+                  AND ($1::text IS NULL OR category = $1)
+                ORDER BY created_at DESC
+                LIMIT $2
+                "#,
+                &[&category, &limit],
+            )
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| crate::db::EstimationSampleRecord {
+                category: row.get("category"),
+                estimated_cost: row.get("estimated_cost"),
+                actual_cost: row.get("actual_cost"),
+                estimated_time_secs: row.get("estimated_time_secs"),
+                actual_time_secs: row.get("actual_time_secs"),
+                created_at: row.get("created_at"),
+            })
+            .collect())
+    }
 }
 
 // ==================== Sandbox Jobs ====================
@@ -559,7 +599,7 @@ impl Store {
 
     /// List all sandbox jobs, most recent first.
     pub async fn list_sandbox_jobs(&self) -> Result<Vec<SandboxJobRecord>, DatabaseError> {
-        let conn = self.conn().await?;
+        let conn = self.read_conn().await?;
         let rows = conn
             .query(
                 r#"
@@ -597,7 +637,7 @@ impl Store {
         &self,
         user_id: &str,
     ) -> Result<Vec<SandboxJobRecord>, DatabaseError> {
-        let conn = self.conn().await?;
+        let conn = self.read_conn().await?;
         let rows = conn
             .query(
                 r#"
@@ -650,10 +690,10 @@ impl Store {
             let c = count as usize;
             summary.total += c;
             match status.as_str() {
-                "creating" => summary.creating += c,
+                "creating" | "queued" => summary.creating += c,
                 "running" => summary.running += c,
                 "completed" => summary.completed += c,
-                "failed" => summary.failed += c,
+                "failed" | "dead_letter" => summary.failed += c,
                 "interrupted" => summary.interrupted += c,
                 _ => {}
             }
@@ -704,7 +744,7 @@ impl Store {
         Ok(())
     }
 
-    /// Mark any sandbox jobs left in "running" or "creating" as "interrupted".
+    /// Mark any sandbox jobs left in "running", "creating", or "queued" as "interrupted".
     ///
     /// Called on startup to handle jobs that were running when the process died.
     pub async fn cleanup_stale_sandbox_jobs(&self) -> Result<u64, DatabaseError> {
@@ -716,7 +756,7 @@ impl Store {
                     status = 'interrupted',
                     failure_reason = 'Process restarted',
                     completed_at = NOW()
-                WHERE source = 'sandbox' AND status IN ('running', 'creating')
+                WHERE source = 'sandbox' AND status IN ('running', 'creating', 'queued')
                 "#,
                 &[],
             )
@@ -744,10 +784,10 @@ impl Store {
             let c = count as usize;
             summary.total += c;
             match status.as_str() {
-                "creating" => summary.creating += c,
+                "creating" | "queued" => summary.creating += c,
                 "running" => summary.running += c,
                 "completed" => summary.completed += c,
-                "failed" => summary.failed += c,
+                "failed" | "dead_letter" => summary.failed += c,
                 "interrupted" => summary.interrupted += c,
                 _ => {}
             }
@@ -840,6 +880,39 @@ impl Store {
             .collect())
     }
 
+    /// Load up to `limit` events with an id greater than `after_id`, oldest
+    /// first. Used to tail a job and to resume a dropped stream.
+    pub async fn list_job_events_after(
+        &self,
+        job_id: Uuid,
+        after_id: i64,
+        limit: i64,
+    ) -> Result<Vec<JobEventRecord>, DatabaseError> {
+        let conn = self.conn().await?;
+        let rows = conn
+            .query(
+                r#"
+                SELECT id, job_id, event_type, data, created_at
+                FROM job_events
+                WHERE job_id = $1 AND id > $2
+                ORDER BY id ASC
+                LIMIT $3
+                "#,
+                &[&job_id, &after_id, &limit],
+            )
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|r| JobEventRecord {
+                id: r.get("id"),
+                job_id: r.get("job_id"),
+                event_type: r.get("event_type"),
+                data: r.get("data"),
+                created_at: r.get("created_at"),
+            })
+            .collect())
+    }
+
     /// Update the job_mode column for a sandbox job.
     pub async fn update_sandbox_job_mode(&self, id: Uuid, mode: &str) -> Result<(), DatabaseError> {
         let conn = self.conn().await?;
@@ -859,6 +932,149 @@ impl Store {
             .await?;
         Ok(row.map(|r| r.get("job_mode")))
     }
+
+    /// Update the priority column for a sandbox job.
+    pub async fn update_sandbox_job_priority(
+        &self,
+        id: Uuid,
+        priority: &str,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.conn().await?;
+        conn.execute(
+            "UPDATE agent_jobs SET priority = $2 WHERE id = $1",
+            &[&id, &priority],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Get the priority for a sandbox job.
+    pub async fn get_sandbox_job_priority(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<String>, DatabaseError> {
+        let conn = self.conn().await?;
+        let row = conn
+            .query_opt("SELECT priority FROM agent_jobs WHERE id = $1", &[&id])
+            .await?;
+        Ok(row.map(|r| r.get("priority")))
+    }
+
+    /// Register a job artifact, replacing an earlier row with the same name.
+    pub async fn save_job_artifact(
+        &self,
+        artifact: &crate::db::JobArtifactRecord,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.conn().await?;
+        conn.execute(
+            r#"
+            INSERT INTO job_artifacts (
+                job_id, name, size_bytes, content_type, storage_path, promoted_to, created_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (job_id, name) DO UPDATE SET
+                size_bytes = EXCLUDED.size_bytes,
+                content_type = EXCLUDED.content_type,
+                storage_path = EXCLUDED.storage_path,
+                promoted_to = EXCLUDED.promoted_to,
+                created_at = EXCLUDED.created_at
+            "#,
+            &[
+                &artifact.job_id,
+                &artifact.name,
+                &artifact.size_bytes,
+                &artifact.content_type,
+                &artifact.storage_path,
+                &artifact.promoted_to,
+                &artifact.created_at,
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// List a job's artifacts ordered by name.
+    pub async fn list_job_artifacts(
+        &self,
+        job_id: Uuid,
+    ) -> Result<Vec<crate::db::JobArtifactRecord>, DatabaseError> {
+        let conn = self.conn().await?;
+        let rows = conn
+            .query(
+                r#"
+                SELECT job_id, name, size_bytes, content_type, storage_path, promoted_to, created_at
+                FROM job_artifacts WHERE job_id = $1 ORDER BY name
+                "#,
+                &[&job_id],
+            )
+            .await?;
+        Ok(rows.iter().map(row_to_job_artifact).collect())
+    }
+
+    /// Get one artifact by name.
+    pub async fn get_job_artifact(
+        &self,
+        job_id: Uuid,
+        name: &str,
+    ) -> Result<Option<crate::db::JobArtifactRecord>, DatabaseError> {
+        let conn = self.conn().await?;
+        let row = conn
+            .query_opt(
+                r#"
+                SELECT job_id, name, size_bytes, content_type, storage_path, promoted_to, created_at
+                FROM job_artifacts WHERE job_id = $1 AND name = $2
+                "#,
+                &[&job_id, &name],
+            )
+            .await?;
+        Ok(row.as_ref().map(row_to_job_artifact))
+    }
+
+    /// Record the workspace path an artifact was promoted to.
+    pub async fn mark_job_artifact_promoted(
+        &self,
+        job_id: Uuid,
+        name: &str,
+        promoted_to: &str,
+    ) -> Result<(), DatabaseError> {
+        let conn = self.conn().await?;
+        conn.execute(
+            "UPDATE job_artifacts SET promoted_to = $3 WHERE job_id = $1 AND name = $2",
+            &[&job_id, &name, &promoted_to],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Delete artifacts registered before `cutoff` and return them.
+    pub async fn delete_job_artifacts_before(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<crate::db::JobArtifactRecord>, DatabaseError> {
+        let conn = self.conn().await?;
+        let rows = conn
+            .query(
+                r#"
+                DELETE FROM job_artifacts WHERE created_at < $1
+                RETURNING job_id, name, size_bytes, content_type, storage_path, promoted_to, created_at
+                "#,
+                &[&cutoff],
+            )
+            .await?;
+        Ok(rows.iter().map(row_to_job_artifact).collect())
+    }
+}
+
+#[cfg(feature = "postgres")]
+fn row_to_job_artifact(row: &tokio_postgres::Row) -> crate::db::JobArtifactRecord {
+    crate::db::JobArtifactRecord {
+        job_id: row.get("job_id"),
+        name: row.get("name"),
+        size_bytes: row.get("size_bytes"),
+        content_type: row.get("content_type"),
+        storage_path: row.get("storage_path"),
+        promoted_to: row.get("promoted_to"),
+        created_at: row.get("created_at"),
+    }
 }
 
 // ==================== Routines ====================
@@ -880,6 +1096,8 @@ impl Store {
         let cooldown_secs = routine.guardrails.cooldown.as_secs() as i32;
         let max_concurrent = routine.guardrails.max_concurrent as i32;
         let dedup_window_secs = routine.guardrails.dedup_window.map(|d| d.as_secs() as i32);
+        let execution_override = routine.guardrails.execution_json();
+        let chain = routine.chain.to_json();
 
         conn.execute(
             r#"
@@ -888,13 +1106,13 @@ impl Store {
                 trigger_type, trigger_config, action_type, action_config,
                 cooldown_secs, max_concurrent, dedup_window_secs,
                 notify_channel, notify_user, notify_on_success, notify_on_failure, notify_on_attention,
-                state, next_fire_at, created_at, updated_at
+                state, next_fire_at, created_at, updated_at, urgent, execution_override, chain
             ) VALUES (
                 $1, $2, $3, $4, $5,
                 $6, $7, $8, $9,
                 $10, $11, $12,
                 $13, $14, $15, $16, $17,
-                $18, $19, $20, $21
+                $18, $19, $20, $21, $22, $23, $24
             )
             "#,
             &[
@@ -919,6 +1137,9 @@ impl Store {
                 &routine.next_fire_at,
                 &routine.created_at,
                 &routine.updated_at,
+                &routine.guardrails.urgent,
+                &execution_override,
+                &chain,
             ],
         )
         .await?;
@@ -1004,6 +1225,8 @@ impl Store {
         let cooldown_secs = routine.guardrails.cooldown.as_secs() as i32;
         let max_concurrent = routine.guardrails.max_concurrent as i32;
         let dedup_window_secs = routine.guardrails.dedup_window.map(|d| d.as_secs() as i32);
+        let execution_override = routine.guardrails.execution_json();
+        let chain = routine.chain.to_json();
 
         conn.execute(
             r#"
@@ -1015,7 +1238,7 @@ impl Store {
                 notify_channel = $12, notify_user = $13,
                 notify_on_success = $14, notify_on_failure = $15, notify_on_attention = $16,
                 state = $17, next_fire_at = $18,
-                updated_at = now()
+                urgent = $19, execution_override = $20, chain = $21, updated_at = now()
             WHERE id = $1
             "#,
             &[
@@ -1037,6 +1260,9 @@ impl Store {
                 &routine.notify.on_attention,
                 &routine.state,
                 &routine.next_fire_at,
+                &routine.guardrails.urgent,
+                &execution_override,
+                &chain,
             ],
         )
         .await?;
@@ -1141,7 +1367,7 @@ impl Store {
         routine_id: Uuid,
         limit: i64,
     ) -> Result<Vec<RoutineRun>, DatabaseError> {
-        let conn = self.conn().await?;
+        let conn = self.read_conn().await?;
         let rows = conn
             .query(
                 r#"
@@ -1211,6 +1437,8 @@ fn row_to_routine(row: &tokio_postgres::Row) -> Result<Routine, DatabaseError> {
             cooldown: std::time::Duration::from_secs(cooldown_secs as u64),
             max_concurrent: max_concurrent as u32,
             dedup_window: dedup_window_secs.map(|s| std::time::Duration::from_secs(s as u64)),
+            urgent: row.get("urgent"),
+            execution: serde_json::from_value(row.get("execution_override")).unwrap_or_default(),
         },
         notify: NotifyConfig {
             channel: row.get("notify_channel"),
@@ -1219,6 +1447,7 @@ fn row_to_routine(row: &tokio_postgres::Row) -> Result<Routine, DatabaseError> {
             on_failure: row.get("notify_on_failure"),
             on_success: row.get("notify_on_success"),
         },
+        chain: serde_json::from_value(row.get("chain")).unwrap_or_default(),
         last_run_at: row.get("last_run_at"),
         next_fire_at: row.get("next_fire_at"),
         run_count: row.get::<_, i64>("run_count") as u64,
@@ -1309,7 +1538,7 @@ impl Store {
         channel: &str,
         limit: i64,
     ) -> Result<Vec<ConversationSummary>, DatabaseError> {
-        let conn = self.conn().await?;
+        let conn = self.read_conn().await?;
         let rows = conn
             .query(
                 r#"
@@ -1364,7 +1593,7 @@ impl Store {
         matter_id: &str,
         limit: i64,
     ) -> Result<Vec<ConversationSummary>, DatabaseError> {
-        let conn = self.conn().await?;
+        let conn = self.read_conn().await?;
         let rows = conn
             .query(
                 r#"
@@ -637,7 +637,10 @@ fn summarize_webhook_event(event: &HookEvent) -> OutboundWebhookEventSummary {
     }
 }
 
-fn validate_webhook_url(hook_name: &str, url: &str) -> Result<reqwest::Url, HookBundleError> {
+pub(crate) fn validate_webhook_url(
+    hook_name: &str,
+    url: &str,
+) -> Result<reqwest::Url, HookBundleError> {
     let parsed = reqwest::Url::parse(url).map_err(|_| HookBundleError::InvalidWebhookUrl {
         hook: hook_name.to_string(),
         url: url.to_string(),
@@ -678,7 +681,7 @@ fn validate_webhook_url(hook_name: &str, url: &str) -> Result<reqwest::Url, Hook
     Ok(parsed)
 }
 
-async fn dispatch_client_for_target(
+pub(crate) async fn dispatch_client_for_target(
     base_client: &reqwest::Client,
     url: &str,
     timeout: Duration,
@@ -0,0 +1,13 @@
+//! Integrations with systems outside the firm's cLawyer instance.
+
+pub mod transcription;
+pub mod webhooks;
+
+pub use transcription::{
+    MAX_AUDIO_BYTES, Transcriber, TranscriptionError, TranscriptionService, VoiceNote,
+    VoiceNoteLog, WhisperApiTranscriber,
+};
+pub use webhooks::{
+    WebhookDelivery, WebhookDeliveryStatus, WebhookDispatcher, WebhookEndpoint,
+    WebhookEndpointUpdate, WebhookError, WebhookEventKind,
+};
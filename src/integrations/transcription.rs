@@ -0,0 +1,595 @@
+//! Voice note transcription for messaging channels.
+//!
+//! WASM channels hand downloaded audio to the host through the
+//! `transcribe-audio` import. The host runs it through the configured
+//! [`Transcriber`] — an OpenAI-compatible Whisper endpoint, or a local
+//! `whisper.cpp` binary when built with the `local-transcription` feature —
+//! and returns the text so the channel can emit it to the agent in place of
+//! the audio.
+//!
+//! When a [`VoiceNoteLog`] is attached, every transcript is also filed in the
+//! communications folder of the sender's owner's active matter, with a row in
+//! the matter contact log pointing at the transcript and the original audio.
+//! Filing happens on a background task so the channel callback never waits
+//! on the workspace.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use secrecy::{ExposeSecret, SecretString};
+use tokio::sync::mpsc;
+
+use crate::db::Database;
+use crate::legal::correspondence::{
+    CONTACT_LOG_TEMPLATE, contact_log_path, render_voice_note, voice_note_path, voice_note_row,
+};
+use crate::workspace::Workspace;
+
+/// Largest audio payload accepted for transcription (the Whisper API limit).
+pub const MAX_AUDIO_BYTES: usize = 25 * 1024 * 1024;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+const MAX_ERROR_CHARS: usize = 300;
+
+/// Errors from transcribing a voice note.
+#[derive(Debug, thiserror::Error)]
+pub enum TranscriptionError {
+    #[error("audio is {size} bytes, limit is {limit}")]
+    TooLarge { size: usize, limit: usize },
+
+    #[error("audio is empty")]
+    Empty,
+
+    #[error("transcription request failed: {0}")]
+    Request(String),
+
+    #[error("transcription backend returned {status}: {body}")]
+    Backend { status: u16, body: String },
+
+    #[error("local transcription failed: {0}")]
+    Local(String),
+}
+
+/// Turns audio into text.
+#[async_trait]
+pub trait Transcriber: Send + Sync + std::fmt::Debug {
+    /// Transcribe `audio` (encoded as `mime_type`, e.g. `audio/ogg`).
+    async fn transcribe(
+        &self,
+        audio: Vec<u8>,
+        mime_type: &str,
+    ) -> Result<String, TranscriptionError>;
+}
+
+/// File extension Whisper servers use to sniff the container format.
+fn audio_extension(mime_type: &str) -> &'static str {
+    let essence = mime_type.split(';').next().unwrap_or("").trim();
+    match essence.to_ascii_lowercase().as_str() {
+        "audio/ogg" | "audio/opus" | "application/ogg" => "ogg",
+        "audio/mpeg" | "audio/mp3" => "mp3",
+        "audio/mp4" | "audio/m4a" | "audio/x-m4a" | "audio/aac" => "m4a",
+        "audio/wav" | "audio/x-wav" | "audio/wave" => "wav",
+        "audio/webm" => "webm",
+        "audio/flac" | "audio/x-flac" => "flac",
+        "audio/amr" => "amr",
+        _ => "ogg",
+    }
+}
+
+fn truncate_error(body: &str) -> String {
+    let mut out: String = body.trim().chars().take(MAX_ERROR_CHARS).collect();
+    if out.len() < body.trim().len() {
+        out.push('…');
+    }
+    out
+}
+
+/// OpenAI-compatible `/audio/transcriptions` client (OpenAI, Groq,
+/// faster-whisper-server, whisper.cpp's `server`, ...).
+#[derive(Debug)]
+pub struct WhisperApiTranscriber {
+    base_url: String,
+    api_key: Option<SecretString>,
+    model: String,
+    language: Option<String>,
+}
+
+impl WhisperApiTranscriber {
+    pub fn new(
+        base_url: String,
+        api_key: Option<SecretString>,
+        model: String,
+        language: Option<String>,
+    ) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key,
+            model,
+            language,
+        }
+    }
+}
+
+#[async_trait]
+impl Transcriber for WhisperApiTranscriber {
+    async fn transcribe(
+        &self,
+        audio: Vec<u8>,
+        mime_type: &str,
+    ) -> Result<String, TranscriptionError> {
+        // Built per call: channel host calls run on a short-lived runtime, and
+        // a pooled client must not outlive the runtime its connections use.
+        let client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(10))
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| TranscriptionError::Request(e.to_string()))?;
+
+        let file = reqwest::multipart::Part::bytes(audio)
+            .file_name(format!("voice.{}", audio_extension(mime_type)))
+            .mime_str(mime_type)
+            .map_err(|e| TranscriptionError::Request(e.to_string()))?;
+        let mut form = reqwest::multipart::Form::new()
+            .text("model", self.model.clone())
+            .text("response_format", "json")
+            .part("file", file);
+        if let Some(language) = &self.language {
+            form = form.text("language", language.clone());
+        }
+
+        let mut request = client
+            .post(format!("{}/audio/transcriptions", self.base_url))
+            .multipart(form);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key.expose_secret());
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| TranscriptionError::Request(e.to_string()))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| TranscriptionError::Request(e.to_string()))?;
+        if !status.is_success() {
+            return Err(TranscriptionError::Backend {
+                status: status.as_u16(),
+                body: truncate_error(&body),
+            });
+        }
+
+        let parsed: serde_json::Value = serde_json::from_str(&body)
+            .map_err(|e| TranscriptionError::Request(format!("invalid response: {e}")))?;
+        parsed
+            .get("text")
+            .and_then(|t| t.as_str())
+            .map(|t| t.trim().to_string())
+            .ok_or_else(|| TranscriptionError::Request("response has no text".to_string()))
+    }
+}
+
+/// Local transcription with the `whisper.cpp` CLI. Channel audio (usually
+/// Opus in Ogg) is converted to 16 kHz mono WAV with `ffmpeg` first.
+#[cfg(feature = "local-transcription")]
+#[derive(Debug)]
+pub struct WhisperCppTranscriber {
+    binary: String,
+    model: std::path::PathBuf,
+    ffmpeg: String,
+    language: Option<String>,
+}
+
+#[cfg(feature = "local-transcription")]
+impl WhisperCppTranscriber {
+    pub fn new(
+        binary: String,
+        model: std::path::PathBuf,
+        ffmpeg: String,
+        language: Option<String>,
+    ) -> Self {
+        Self {
+            binary,
+            model,
+            ffmpeg,
+            language,
+        }
+    }
+
+    async fn run(
+        &self,
+        input: &std::path::Path,
+        wav: &std::path::Path,
+    ) -> Result<String, TranscriptionError> {
+        let converted = tokio::process::Command::new(&self.ffmpeg)
+            .args(["-nostdin", "-loglevel", "error", "-y", "-i"])
+            .arg(input)
+            .args(["-ar", "16000", "-ac", "1", "-c:a", "pcm_s16le"])
+            .arg(wav)
+            .output()
+            .await
+            .map_err(|e| {
+                TranscriptionError::Local(format!("failed to run {}: {e}", self.ffmpeg))
+            })?;
+        if !converted.status.success() {
+            return Err(TranscriptionError::Local(format!(
+                "ffmpeg: {}",
+                truncate_error(&String::from_utf8_lossy(&converted.stderr))
+            )));
+        }
+
+        let mut command = tokio::process::Command::new(&self.binary);
+        command
+            .arg("-m")
+            .arg(&self.model)
+            .arg("-f")
+            .arg(wav)
+            .args(["-nt", "-np"]);
+        if let Some(language) = &self.language {
+            command.args(["-l", language]);
+        }
+        let output = tokio::time::timeout(REQUEST_TIMEOUT, command.output())
+            .await
+            .map_err(|_| TranscriptionError::Local("whisper.cpp timed out".to_string()))?
+            .map_err(|e| {
+                TranscriptionError::Local(format!("failed to run {}: {e}", self.binary))
+            })?;
+        if !output.status.success() {
+            return Err(TranscriptionError::Local(format!(
+                "whisper.cpp: {}",
+                truncate_error(&String::from_utf8_lossy(&output.stderr))
+            )));
+        }
+        let text = String::from_utf8_lossy(&output.stdout);
+        Ok(text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+}
+
+#[cfg(feature = "local-transcription")]
+#[async_trait]
+impl Transcriber for WhisperCppTranscriber {
+    async fn transcribe(
+        &self,
+        audio: Vec<u8>,
+        mime_type: &str,
+    ) -> Result<String, TranscriptionError> {
+        let stem = std::env::temp_dir().join(format!("clawyer-voice-{}", uuid::Uuid::new_v4()));
+        let input = stem.with_extension(audio_extension(mime_type));
+        let wav = stem.with_extension("16k.wav");
+        tokio::fs::write(&input, &audio)
+            .await
+            .map_err(|e| TranscriptionError::Local(format!("failed to stage audio: {e}")))?;
+        let result = self.run(&input, &wav).await;
+        let _ = tokio::fs::remove_file(&input).await;
+        let _ = tokio::fs::remove_file(&wav).await;
+        result
+    }
+}
+
+/// A transcribed voice note waiting to be filed.
+#[derive(Debug, Clone)]
+pub struct VoiceNote {
+    /// Channel the note arrived on, e.g. `telegram`.
+    pub channel: String,
+    /// Sender as the channel identifies them.
+    pub sender: String,
+    /// Where the original audio lives (channel file ID or workspace path).
+    pub audio_ref: String,
+    pub mime_type: String,
+    pub transcript: String,
+    pub received_at: DateTime<Utc>,
+}
+
+/// Files voice note transcripts under the owner's active matter.
+pub struct VoiceNoteLog {
+    workspace: Arc<Workspace>,
+    store: Arc<dyn Database>,
+    owner_id: String,
+    matter_root: String,
+    /// Serializes contact log read-modify-write appends.
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+impl VoiceNoteLog {
+    pub fn new(
+        workspace: Arc<Workspace>,
+        store: Arc<dyn Database>,
+        owner_id: impl Into<String>,
+        matter_root: impl Into<String>,
+    ) -> Self {
+        Self {
+            workspace,
+            store,
+            owner_id: owner_id.into(),
+            matter_root: matter_root.into(),
+            write_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Write the transcript and append a contact log row. Returns the
+    /// transcript path, or `None` when no matter is active.
+    pub async fn record(&self, note: &VoiceNote) -> Option<String> {
+        let matter_id =
+            crate::agent::cost_guard::active_matter_for_user(self.store.as_ref(), &self.owner_id)
+                .await?;
+        let path = voice_note_path(
+            &self.matter_root,
+            &matter_id,
+            note.received_at,
+            &note.channel,
+        );
+        let doc = render_voice_note(
+            &note.channel,
+            &note.sender,
+            note.received_at,
+            &note.mime_type,
+            &note.audio_ref,
+            &note.transcript,
+        );
+        if let Err(e) = self.workspace.write(&path, &doc).await {
+            tracing::warn!(matter_id = %matter_id, "Failed to file voice note transcript: {e}");
+            return None;
+        }
+
+        let log_path = contact_log_path(&self.matter_root, &matter_id);
+        let _guard = self.write_lock.lock().await;
+        let existing = match self.workspace.read(&log_path).await {
+            Ok(doc) if !doc.content.trim().is_empty() => doc.content,
+            _ => CONTACT_LOG_TEMPLATE.to_string(),
+        };
+        let row = voice_note_row(
+            note.received_at,
+            &note.sender,
+            &note.channel,
+            &note.transcript,
+            &path,
+        );
+        let updated = format!("{}\n{}", existing.trim_end(), row);
+        if let Err(e) = self.workspace.write(&log_path, &updated).await {
+            tracing::warn!(matter_id = %matter_id, "Failed to append voice note to contact log: {e}");
+        }
+        Some(path)
+    }
+}
+
+/// Transcription shared by all WASM channels.
+pub struct TranscriptionService {
+    transcriber: Arc<dyn Transcriber>,
+    notes: Option<mpsc::UnboundedSender<VoiceNote>>,
+}
+
+impl std::fmt::Debug for TranscriptionService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TranscriptionService")
+            .field("transcriber", &self.transcriber)
+            .field("voice_note_log", &self.notes.is_some())
+            .finish()
+    }
+}
+
+impl TranscriptionService {
+    pub fn new(transcriber: Arc<dyn Transcriber>) -> Self {
+        Self {
+            transcriber,
+            notes: None,
+        }
+    }
+
+    /// File every transcript with `log` on a background task.
+    pub fn with_voice_note_log(mut self, log: VoiceNoteLog) -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel::<VoiceNote>();
+        tokio::spawn(async move {
+            while let Some(note) = rx.recv().await {
+                log.record(&note).await;
+            }
+        });
+        self.notes = Some(tx);
+        self
+    }
+
+    /// Transcribe a voice note, enforcing [`MAX_AUDIO_BYTES`].
+    pub async fn transcribe(
+        &self,
+        audio: Vec<u8>,
+        mime_type: &str,
+    ) -> Result<String, TranscriptionError> {
+        if audio.is_empty() {
+            return Err(TranscriptionError::Empty);
+        }
+        if audio.len() > MAX_AUDIO_BYTES {
+            return Err(TranscriptionError::TooLarge {
+                size: audio.len(),
+                limit: MAX_AUDIO_BYTES,
+            });
+        }
+        self.transcriber.transcribe(audio, mime_type).await
+    }
+
+    /// Queue a transcript for filing. No-op without a voice note log.
+    pub fn record(&self, note: VoiceNote) {
+        if let Some(tx) = &self.notes {
+            let _ = tx.send(note);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct FixedTranscriber(&'static str);
+
+    #[async_trait]
+    impl Transcriber for FixedTranscriber {
+        async fn transcribe(
+            &self,
+            _audio: Vec<u8>,
+            _mime_type: &str,
+        ) -> Result<String, TranscriptionError> {
+            Ok(self.0.to_string())
+        }
+    }
+
+    #[test]
+    fn audio_extensions_follow_mime_type() {
+        assert_eq!(audio_extension("audio/ogg; codecs=opus"), "ogg");
+        assert_eq!(audio_extension("audio/mpeg"), "mp3");
+        assert_eq!(audio_extension("AUDIO/MP4"), "m4a");
+        assert_eq!(audio_extension("application/octet-stream"), "ogg");
+    }
+
+    #[tokio::test]
+    async fn service_rejects_empty_and_oversized_audio() {
+        let service = TranscriptionService::new(Arc::new(FixedTranscriber("hello")));
+        assert!(matches!(
+            service.transcribe(Vec::new(), "audio/ogg").await,
+            Err(TranscriptionError::Empty)
+        ));
+        assert!(matches!(
+            service
+                .transcribe(vec![0; MAX_AUDIO_BYTES + 1], "audio/ogg")
+                .await,
+            Err(TranscriptionError::TooLarge { .. })
+        ));
+        assert_eq!(
+            service
+                .transcribe(vec![1, 2, 3], "audio/ogg")
+                .await
+                .unwrap(),
+            "hello"
+        );
+    }
+
+    #[tokio::test]
+    async fn whisper_api_posts_multipart_audio() {
+        use axum::extract::Multipart;
+        use axum::http::HeaderMap;
+
+        async fn handler(headers: HeaderMap, mut form: Multipart) -> axum::Json<serde_json::Value> {
+            let auth = headers
+                .get("authorization")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            let mut fields = Vec::new();
+            while let Some(field) = form.next_field().await.unwrap() {
+                let name = field.name().unwrap_or_default().to_string();
+                let file_name = field.file_name().map(str::to_string);
+                let bytes = field.bytes().await.unwrap();
+                fields.push(format!(
+                    "{name}={}{}",
+                    file_name.map(|f| format!("[{f}]")).unwrap_or_default(),
+                    bytes.len()
+                ));
+            }
+            axum::Json(serde_json::json!({
+                "text": format!("  {auth} {}  ", fields.join(","))
+            }))
+        }
+
+        let app =
+            axum::Router::new().route("/v1/audio/transcriptions", axum::routing::post(handler));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let transcriber = WhisperApiTranscriber::new(
+            format!("http://{addr}/v1/"),
+            Some(SecretString::from("sk-test".to_string())),
+            "whisper-1".to_string(),
+            Some("en".to_string()),
+        );
+        let text = transcriber
+            .transcribe(vec![7; 42], "audio/ogg")
+            .await
+            .expect("transcription should succeed");
+        assert_eq!(
+            text,
+            "Bearer sk-test model=9,response_format=4,file=[voice.ogg]42,language=2"
+        );
+    }
+
+    #[tokio::test]
+    async fn whisper_api_surfaces_backend_errors() {
+        let app = axum::Router::new().route(
+            "/audio/transcriptions",
+            axum::routing::post(|| async {
+                (axum::http::StatusCode::UNAUTHORIZED, "invalid api key")
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let transcriber = WhisperApiTranscriber::new(
+            format!("http://{addr}"),
+            None,
+            "whisper-1".to_string(),
+            None,
+        );
+        match transcriber.transcribe(vec![1], "audio/ogg").await {
+            Err(TranscriptionError::Backend { status, body }) => {
+                assert_eq!(status, 401);
+                assert_eq!(body, "invalid api key");
+            }
+            other => panic!("expected backend error, got {other:?}"),
+        }
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn voice_notes_file_under_active_matter() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", db.clone()));
+        let log = VoiceNoteLog::new(Arc::clone(&workspace), db.clone(), "default", "matters");
+        let note = VoiceNote {
+            channel: "telegram".to_string(),
+            sender: "Jane Client".to_string(),
+            audio_ref: "telegram:file/AwACAgQ".to_string(),
+            mime_type: "audio/ogg".to_string(),
+            transcript: "Please call me about the lease.".to_string(),
+            received_at: chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 10, 15, 9, 30, 5).unwrap(),
+        };
+
+        // Nothing is filed without an active matter.
+        assert_eq!(log.record(&note).await, None);
+
+        db.set_setting(
+            "default",
+            "legal.active_matter",
+            &serde_json::json!("acme-v-foo"),
+        )
+        .await
+        .unwrap();
+        let path = log.record(&note).await.expect("should file transcript");
+        assert_eq!(
+            path,
+            "matters/acme-v-foo/communications/voice/2026-10-15-093005-telegram.md"
+        );
+        let doc = workspace.read(&path).await.unwrap();
+        assert!(doc.content.contains("Audio: telegram:file/AwACAgQ"));
+        assert!(doc.content.contains("Please call me about the lease."));
+
+        let contact_log = workspace
+            .read("matters/acme-v-foo/communications/contact_log.md")
+            .await
+            .unwrap();
+        assert!(contact_log.content.starts_with("# Communications Log"));
+        assert!(contact_log.content.contains(&format!(
+            "| Jane Client | telegram | Voice note \"Please call me about the lease.\" ({path}) |"
+        )));
+    }
+}
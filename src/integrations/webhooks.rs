@@ -0,0 +1,1009 @@
+//! Outbound webhooks for internal firm events.
+//!
+//! Administrators register HTTPS endpoints and subscribe them to event kinds
+//! (matter created, deadline added, job completed, conflict hit, document
+//! generated). The dispatcher listens on the domain [`EventBus`]. Every
+//! delivery is signed with the endpoint's secret, retried with exponential
+//! backoff on network errors, 429s, and 5xx responses, and recorded in an
+//! in-memory delivery log that the web API exposes per endpoint.
+//!
+//! Endpoints persist in the settings table under [`WEBHOOKS_SETTING_KEY`].
+//! Targets go through the same SSRF checks as bundled outbound webhook hooks:
+//! https only, no embedded credentials, and no private or loopback hosts,
+//! with DNS pinned to the addresses that were checked.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::sync::{RwLock, Semaphore};
+use uuid::Uuid;
+
+use crate::db::Database;
+use crate::events::{DomainEvent, EventBus};
+use crate::hooks::bundled::{dispatch_client_for_target, validate_webhook_url};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Settings key holding the configured endpoints.
+pub const WEBHOOKS_SETTING_KEY: &str = "integrations.webhooks";
+
+/// Header carrying `t=<unix seconds>,v1=<hex HMAC-SHA256>`.
+pub const SIGNATURE_HEADER: &str = "X-Clawyer-Signature";
+/// Header carrying the event kind, e.g. `matter.created`.
+pub const EVENT_HEADER: &str = "X-Clawyer-Event";
+/// Header carrying the delivery ID (stable across retries).
+pub const DELIVERY_HEADER: &str = "X-Clawyer-Delivery";
+
+const MAX_ENDPOINTS: usize = 25;
+const MAX_NAME_CHARS: usize = 100;
+const MAX_DELIVERY_LOG: usize = 500;
+const MAX_IN_FLIGHT: usize = 8;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_ERROR_CHARS: usize = 300;
+
+/// Internal events an endpoint can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WebhookEventKind {
+    #[serde(rename = "matter.created")]
+    MatterCreated,
+    #[serde(rename = "deadline.added")]
+    DeadlineAdded,
+    #[serde(rename = "job.completed")]
+    JobCompleted,
+    #[serde(rename = "conflict.hit")]
+    ConflictHit,
+    #[serde(rename = "document.generated")]
+    DocumentGenerated,
+    /// Sent by the "test endpoint" action; not subscribable.
+    #[serde(rename = "webhook.test")]
+    Test,
+}
+
+impl WebhookEventKind {
+    /// Event kinds endpoints can subscribe to.
+    pub const SUBSCRIBABLE: [WebhookEventKind; 5] = [
+        Self::MatterCreated,
+        Self::DeadlineAdded,
+        Self::JobCompleted,
+        Self::ConflictHit,
+        Self::DocumentGenerated,
+    ];
+
+    /// The webhook kind a domain event is delivered as.
+    pub fn for_event(event: &DomainEvent) -> Self {
+        match event {
+            DomainEvent::MatterCreated { .. } => Self::MatterCreated,
+            DomainEvent::DeadlineAdded { .. } => Self::DeadlineAdded,
+            DomainEvent::ConflictHit { .. } => Self::ConflictHit,
+            DomainEvent::JobCompleted { .. } => Self::JobCompleted,
+            DomainEvent::DocumentGenerated { .. } => Self::DocumentGenerated,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MatterCreated => "matter.created",
+            Self::DeadlineAdded => "deadline.added",
+            Self::JobCompleted => "job.completed",
+            Self::ConflictHit => "conflict.hit",
+            Self::DocumentGenerated => "document.generated",
+            Self::Test => "webhook.test",
+        }
+    }
+}
+
+impl std::str::FromStr for WebhookEventKind {
+    type Err = WebhookError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Self::SUBSCRIBABLE
+            .into_iter()
+            .find(|kind| kind.as_str() == raw.trim())
+            .ok_or_else(|| WebhookError::InvalidEvent(raw.to_string()))
+    }
+}
+
+/// Errors from managing webhook endpoints.
+#[derive(Debug, thiserror::Error)]
+pub enum WebhookError {
+    #[error("Webhook endpoint {0} not found")]
+    NotFound(Uuid),
+
+    #[error("Invalid webhook URL: {0}")]
+    InvalidUrl(String),
+
+    #[error("Unknown webhook event '{0}'")]
+    InvalidEvent(String),
+
+    #[error("Webhook endpoint must subscribe to at least one event")]
+    NoEvents,
+
+    #[error("Webhook endpoint name must be 1-{MAX_NAME_CHARS} characters")]
+    InvalidName,
+
+    #[error("At most {MAX_ENDPOINTS} webhook endpoints can be configured")]
+    TooManyEndpoints,
+
+    #[error("Failed to persist webhook endpoints: {0}")]
+    Storage(String),
+}
+
+/// A configured delivery target.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookEndpoint {
+    pub id: Uuid,
+    pub name: String,
+    pub url: String,
+    pub events: Vec<WebhookEventKind>,
+    /// Signing secret; only returned to callers on create and rotation.
+    pub secret: String,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookEndpoint {
+    /// Last few characters of the secret, for telling secrets apart in lists.
+    pub fn secret_hint(&self) -> String {
+        let tail: String = self
+            .secret
+            .chars()
+            .rev()
+            .take(4)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect();
+        format!("whsec_…{tail}")
+    }
+
+    fn subscribes_to(&self, kind: WebhookEventKind) -> bool {
+        self.enabled && self.events.contains(&kind)
+    }
+}
+
+/// Partial update for an endpoint; `None` leaves a field unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct WebhookEndpointUpdate {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub events: Option<Vec<WebhookEventKind>>,
+    pub enabled: Option<bool>,
+    pub rotate_secret: bool,
+}
+
+/// Where a delivery is in its retry lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    /// Last attempt failed with a retryable error; another is scheduled.
+    Retrying,
+    Delivered,
+    /// Gave up: a non-retryable response, or attempts exhausted.
+    Failed,
+}
+
+/// One event sent (or being sent) to one endpoint.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub endpoint_id: Uuid,
+    pub event_id: Uuid,
+    pub event: WebhookEventKind,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    pub response_status: Option<u16>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+}
+
+/// Attempt budget and backoff schedule.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts including the first.
+    pub max_attempts: u32,
+    /// Delay before the second attempt; each later delay is 4x the previous.
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// 5 attempts spaced ~2s, 8s, 32s, 128s apart.
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(2),
+            max_delay: Duration::from_secs(300),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay after failed attempt number `attempt` (1-based).
+    pub fn delay_after(&self, attempt: u32) -> Duration {
+        let factor = 4u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+enum AttemptOutcome {
+    Delivered(u16),
+    Retryable(Option<u16>, String),
+    Permanent(Option<u16>, String),
+}
+
+/// Signature header value for `body` sent at `timestamp`.
+///
+/// Receivers recompute `HMAC-SHA256(secret, "<t>.<body>")`, compare it with
+/// `v1` in constant time, and reject stale timestamps to prevent replays.
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    format!("t={timestamp},v1={}", hex(&mac.finalize().into_bytes()))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("whsec_{}", hex(&bytes))
+}
+
+fn truncate_error(raw: &str) -> String {
+    if raw.chars().count() <= MAX_ERROR_CHARS {
+        raw.to_string()
+    } else {
+        let mut out: String = raw.chars().take(MAX_ERROR_CHARS).collect();
+        out.push('…');
+        out
+    }
+}
+
+fn normalize_name(raw: &str) -> Result<String, WebhookError> {
+    let name = raw.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+        return Err(WebhookError::InvalidName);
+    }
+    Ok(name.to_string())
+}
+
+fn normalize_events(events: Vec<WebhookEventKind>) -> Result<Vec<WebhookEventKind>, WebhookError> {
+    let mut out = Vec::new();
+    for kind in events {
+        if kind == WebhookEventKind::Test {
+            return Err(WebhookError::InvalidEvent(kind.as_str().to_string()));
+        }
+        if !out.contains(&kind) {
+            out.push(kind);
+        }
+    }
+    if out.is_empty() {
+        return Err(WebhookError::NoEvents);
+    }
+    Ok(out)
+}
+
+/// Sends internal events to configured endpoints.
+pub struct WebhookDispatcher {
+    store: Option<Arc<dyn Database>>,
+    user_id: String,
+    endpoints: RwLock<Vec<WebhookEndpoint>>,
+    deliveries: Mutex<VecDeque<WebhookDelivery>>,
+    client: reqwest::Client,
+    policy: RetryPolicy,
+    in_flight: Semaphore,
+    /// Test-only escape hatch so tests can target a local plain-HTTP server.
+    allow_private_targets: bool,
+}
+
+impl WebhookDispatcher {
+    fn with_parts(
+        store: Option<Arc<dyn Database>>,
+        user_id: &str,
+        endpoints: Vec<WebhookEndpoint>,
+        policy: RetryPolicy,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap_or_default();
+        Self {
+            store,
+            user_id: user_id.to_string(),
+            endpoints: RwLock::new(endpoints),
+            deliveries: Mutex::new(VecDeque::new()),
+            client,
+            policy,
+            in_flight: Semaphore::new(MAX_IN_FLIGHT),
+            allow_private_targets: false,
+        }
+    }
+
+    /// Load endpoints saved for `user_id`. A missing or unreadable setting
+    /// starts with no endpoints rather than failing startup.
+    pub async fn load(store: Arc<dyn Database>, user_id: &str) -> Self {
+        let endpoints = match store.get_setting(user_id, WEBHOOKS_SETTING_KEY).await {
+            Ok(Some(value)) => serde_json::from_value(value).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring unreadable webhook endpoint settings: {}", e);
+                Vec::new()
+            }),
+            Ok(None) => Vec::new(),
+            Err(e) => {
+                tracing::warn!("Failed to load webhook endpoints: {}", e);
+                Vec::new()
+            }
+        };
+        Self::with_parts(Some(store), user_id, endpoints, RetryPolicy::default())
+    }
+
+    #[cfg(test)]
+    pub(crate) fn for_tests(store: Option<Arc<dyn Database>>, policy: RetryPolicy) -> Self {
+        let mut dispatcher = Self::with_parts(store, "default", Vec::new(), policy);
+        dispatcher.allow_private_targets = true;
+        dispatcher
+    }
+
+    fn validate_url(&self, raw: &str) -> Result<String, WebhookError> {
+        let raw = raw.trim();
+        if self.allow_private_targets {
+            reqwest::Url::parse(raw).map_err(|e| WebhookError::InvalidUrl(e.to_string()))?;
+            return Ok(raw.to_string());
+        }
+        validate_webhook_url("integrations", raw)
+            .map(|url| url.to_string())
+            .map_err(|e| WebhookError::InvalidUrl(e.to_string()))
+    }
+
+    async fn persist(&self, endpoints: &[WebhookEndpoint]) -> Result<(), WebhookError> {
+        let Some(store) = self.store.as_ref() else {
+            return Ok(());
+        };
+        let value =
+            serde_json::to_value(endpoints).map_err(|e| WebhookError::Storage(e.to_string()))?;
+        store
+            .set_setting(&self.user_id, WEBHOOKS_SETTING_KEY, &value)
+            .await
+            .map_err(|e| WebhookError::Storage(e.to_string()))
+    }
+
+    pub async fn list(&self) -> Vec<WebhookEndpoint> {
+        self.endpoints.read().await.clone()
+    }
+
+    pub async fn get(&self, id: Uuid) -> Option<WebhookEndpoint> {
+        self.endpoints
+            .read()
+            .await
+            .iter()
+            .find(|e| e.id == id)
+            .cloned()
+    }
+
+    pub async fn create(
+        &self,
+        name: &str,
+        url: &str,
+        events: Vec<WebhookEventKind>,
+    ) -> Result<WebhookEndpoint, WebhookError> {
+        let now = Utc::now();
+        let endpoint = WebhookEndpoint {
+            id: Uuid::new_v4(),
+            name: normalize_name(name)?,
+            url: self.validate_url(url)?,
+            events: normalize_events(events)?,
+            secret: generate_secret(),
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        };
+        let mut endpoints = self.endpoints.write().await;
+        if endpoints.len() >= MAX_ENDPOINTS {
+            return Err(WebhookError::TooManyEndpoints);
+        }
+        let mut next = endpoints.clone();
+        next.push(endpoint.clone());
+        self.persist(&next).await?;
+        *endpoints = next;
+        Ok(endpoint)
+    }
+
+    pub async fn update(
+        &self,
+        id: Uuid,
+        update: WebhookEndpointUpdate,
+    ) -> Result<WebhookEndpoint, WebhookError> {
+        let name = update.name.as_deref().map(normalize_name).transpose()?;
+        let url = update
+            .url
+            .as_deref()
+            .map(|u| self.validate_url(u))
+            .transpose()?;
+        let events = update.events.map(normalize_events).transpose()?;
+
+        let mut endpoints = self.endpoints.write().await;
+        let mut next = endpoints.clone();
+        let endpoint = next
+            .iter_mut()
+            .find(|e| e.id == id)
+            .ok_or(WebhookError::NotFound(id))?;
+        if let Some(name) = name {
+            endpoint.name = name;
+        }
+        if let Some(url) = url {
+            endpoint.url = url;
+        }
+        if let Some(events) = events {
+            endpoint.events = events;
+        }
+        if let Some(enabled) = update.enabled {
+            endpoint.enabled = enabled;
+        }
+        if update.rotate_secret {
+            endpoint.secret = generate_secret();
+        }
+        endpoint.updated_at = Utc::now();
+        let updated = endpoint.clone();
+        self.persist(&next).await?;
+        *endpoints = next;
+        Ok(updated)
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<WebhookEndpoint, WebhookError> {
+        let mut endpoints = self.endpoints.write().await;
+        let mut next = endpoints.clone();
+        let index = next
+            .iter()
+            .position(|e| e.id == id)
+            .ok_or(WebhookError::NotFound(id))?;
+        let removed = next.remove(index);
+        self.persist(&next).await?;
+        *endpoints = next;
+        Ok(removed)
+    }
+
+    /// Recent deliveries for an endpoint, newest first.
+    pub fn deliveries(&self, endpoint_id: Uuid) -> Vec<WebhookDelivery> {
+        let log = self.deliveries.lock().unwrap_or_else(|e| e.into_inner());
+        log.iter()
+            .rev()
+            .filter(|d| d.endpoint_id == endpoint_id)
+            .cloned()
+            .collect()
+    }
+
+    fn record(&self, delivery: &WebhookDelivery) {
+        let mut log = self.deliveries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(existing) = log.iter_mut().find(|d| d.id == delivery.id) {
+            *existing = delivery.clone();
+            return;
+        }
+        if log.len() >= MAX_DELIVERY_LOG {
+            log.pop_front();
+        }
+        log.push_back(delivery.clone());
+    }
+
+    /// Send `kind` to every enabled endpoint subscribed to it. Returns
+    /// immediately; deliveries and retries run in the background.
+    pub fn emit(self: &Arc<Self>, kind: WebhookEventKind, data: serde_json::Value) {
+        let dispatcher = Arc::clone(self);
+        tokio::spawn(async move {
+            let targets: Vec<Uuid> = dispatcher
+                .endpoints
+                .read()
+                .await
+                .iter()
+                .filter(|e| e.subscribes_to(kind))
+                .map(|e| e.id)
+                .collect();
+            if targets.is_empty() {
+                return;
+            }
+            let body = Arc::new(event_body(kind, data));
+            for endpoint_id in targets {
+                let dispatcher = Arc::clone(&dispatcher);
+                let body = Arc::clone(&body);
+                tokio::spawn(async move {
+                    dispatcher.deliver(endpoint_id, kind, body).await;
+                });
+            }
+        });
+    }
+
+    /// Deliver every domain event published on `bus` to its subscribers.
+    pub fn subscribe(self: &Arc<Self>, bus: &EventBus) -> tokio::task::JoinHandle<()> {
+        let dispatcher = Arc::clone(self);
+        bus.spawn_subscriber("webhooks", move |event| {
+            dispatcher.emit(WebhookEventKind::for_event(&event), event.payload());
+            std::future::ready(())
+        })
+    }
+
+    /// Queue a `webhook.test` delivery to one endpoint, even if it is
+    /// disabled, and return the delivery ID.
+    pub async fn send_test(self: &Arc<Self>, endpoint_id: Uuid) -> Result<Uuid, WebhookError> {
+        let endpoint = self
+            .get(endpoint_id)
+            .await
+            .ok_or(WebhookError::NotFound(endpoint_id))?;
+        let kind = WebhookEventKind::Test;
+        let body = Arc::new(event_body(
+            kind,
+            serde_json::json!({ "endpoint_id": endpoint.id, "name": endpoint.name }),
+        ));
+        let delivery = self.new_delivery(endpoint_id, kind, &body);
+        let id = delivery.id;
+        let dispatcher = Arc::clone(self);
+        tokio::spawn(async move {
+            dispatcher.run_delivery(delivery, body, true).await;
+        });
+        Ok(id)
+    }
+
+    fn new_delivery(
+        &self,
+        endpoint_id: Uuid,
+        kind: WebhookEventKind,
+        body: &EventBody,
+    ) -> WebhookDelivery {
+        let now = Utc::now();
+        let delivery = WebhookDelivery {
+            id: Uuid::new_v4(),
+            endpoint_id,
+            event_id: body.event_id,
+            event: kind,
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            response_status: None,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+            next_attempt_at: Some(now),
+        };
+        self.record(&delivery);
+        delivery
+    }
+
+    async fn deliver(&self, endpoint_id: Uuid, kind: WebhookEventKind, body: Arc<EventBody>) {
+        let delivery = self.new_delivery(endpoint_id, kind, &body);
+        self.run_delivery(delivery, body, false).await;
+    }
+
+    async fn run_delivery(
+        &self,
+        mut delivery: WebhookDelivery,
+        body: Arc<EventBody>,
+        ignore_enabled: bool,
+    ) {
+        loop {
+            // Re-read the endpoint each attempt so deletes, disables, URL
+            // changes, and secret rotations apply to pending retries.
+            let endpoint = match self.get(delivery.endpoint_id).await {
+                Some(e) if e.enabled || ignore_enabled => e,
+                _ => {
+                    delivery.status = WebhookDeliveryStatus::Failed;
+                    delivery.last_error =
+                        Some("Endpoint was deleted or disabled before delivery".to_string());
+                    delivery.next_attempt_at = None;
+                    delivery.updated_at = Utc::now();
+                    self.record(&delivery);
+                    return;
+                }
+            };
+
+            let outcome = {
+                let _permit = self.in_flight.acquire().await;
+                self.attempt(&endpoint, &delivery, &body.json).await
+            };
+            delivery.attempts += 1;
+            delivery.updated_at = Utc::now();
+            let retry = match outcome {
+                AttemptOutcome::Delivered(status) => {
+                    delivery.status = WebhookDeliveryStatus::Delivered;
+                    delivery.response_status = Some(status);
+                    delivery.last_error = None;
+                    false
+                }
+                AttemptOutcome::Retryable(status, error) => {
+                    delivery.response_status = status;
+                    delivery.last_error = Some(truncate_error(&error));
+                    delivery.attempts < self.policy.max_attempts
+                }
+                AttemptOutcome::Permanent(status, error) => {
+                    delivery.response_status = status;
+                    delivery.last_error = Some(truncate_error(&error));
+                    false
+                }
+            };
+
+            if !retry {
+                if delivery.status != WebhookDeliveryStatus::Delivered {
+                    delivery.status = WebhookDeliveryStatus::Failed;
+                    tracing::warn!(
+                        endpoint = %endpoint.id,
+                        event = delivery.event.as_str(),
+                        attempts = delivery.attempts,
+                        "Webhook delivery failed: {}",
+                        delivery.last_error.as_deref().unwrap_or("unknown error")
+                    );
+                }
+                delivery.next_attempt_at = None;
+                self.record(&delivery);
+                return;
+            }
+
+            let delay = self.policy.delay_after(delivery.attempts);
+            delivery.status = WebhookDeliveryStatus::Retrying;
+            delivery.next_attempt_at =
+                Some(Utc::now() + chrono::Duration::from_std(delay).unwrap_or_default());
+            self.record(&delivery);
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    async fn attempt(
+        &self,
+        endpoint: &WebhookEndpoint,
+        delivery: &WebhookDelivery,
+        body: &str,
+    ) -> AttemptOutcome {
+        let client = if self.allow_private_targets {
+            self.client.clone()
+        } else {
+            if let Err(e) = validate_webhook_url("integrations", &endpoint.url) {
+                return AttemptOutcome::Permanent(None, e.to_string());
+            }
+            match dispatch_client_for_target(&self.client, &endpoint.url, REQUEST_TIMEOUT).await {
+                Ok(client) => client,
+                Err(e) => return AttemptOutcome::Retryable(None, e),
+            }
+        };
+
+        let signature = sign_payload(&endpoint.secret, Utc::now().timestamp(), body);
+        let result = client
+            .post(&endpoint.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, signature)
+            .header(EVENT_HEADER, delivery.event.as_str())
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .body(body.to_string())
+            .send()
+            .await;
+
+        match result {
+            Ok(response) => {
+                let status = response.status();
+                let code = status.as_u16();
+                if status.is_success() {
+                    AttemptOutcome::Delivered(code)
+                } else if status.is_server_error()
+                    || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                {
+                    AttemptOutcome::Retryable(Some(code), format!("Endpoint returned {status}"))
+                } else {
+                    AttemptOutcome::Permanent(Some(code), format!("Endpoint returned {status}"))
+                }
+            }
+            Err(e) => AttemptOutcome::Retryable(None, e.to_string()),
+        }
+    }
+}
+
+struct EventBody {
+    event_id: Uuid,
+    json: String,
+}
+
+fn event_body(kind: WebhookEventKind, data: serde_json::Value) -> EventBody {
+    let event_id = Uuid::new_v4();
+    let json = serde_json::json!({
+        "id": event_id,
+        "event": kind.as_str(),
+        "created_at": Utc::now().to_rfc3339(),
+        "data": data,
+    })
+    .to_string();
+    EventBody { event_id, json }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::{Router, http::HeaderMap, http::StatusCode, routing::post};
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(5),
+            max_delay: Duration::from_millis(20),
+        }
+    }
+
+    /// Local receiver that fails the first `fail_first` requests with
+    /// `fail_status`, then returns 200. Captures every request.
+    async fn receiver(
+        fail_first: usize,
+        fail_status: StatusCode,
+    ) -> (String, Arc<Mutex<Vec<(HeaderMap, String)>>>) {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let count = Arc::new(AtomicUsize::new(0));
+        let captured = Arc::clone(&seen);
+        let app = Router::new().route(
+            "/hook",
+            post(move |headers: HeaderMap, body: String| {
+                let captured = Arc::clone(&captured);
+                let count = Arc::clone(&count);
+                async move {
+                    captured.lock().unwrap().push((headers, body));
+                    if count.fetch_add(1, Ordering::SeqCst) < fail_first {
+                        fail_status
+                    } else {
+                        StatusCode::OK
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (format!("http://{addr}/hook"), seen)
+    }
+
+    async fn wait_for_final(dispatcher: &WebhookDispatcher, endpoint_id: Uuid) -> WebhookDelivery {
+        for _ in 0..200 {
+            if let Some(d) = dispatcher.deliveries(endpoint_id).into_iter().next()
+                && matches!(
+                    d.status,
+                    WebhookDeliveryStatus::Delivered | WebhookDeliveryStatus::Failed
+                )
+            {
+                return d;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("delivery did not finish");
+    }
+
+    #[test]
+    fn signature_covers_timestamp_and_body() {
+        let sig = sign_payload("whsec_test", 1_700_000_000, r#"{"a":1}"#);
+        let (t, v1) = sig.split_once(",v1=").unwrap();
+        assert_eq!(t, "t=1700000000");
+        assert_eq!(v1.len(), 64);
+
+        let mut mac = HmacSha256::new_from_slice(b"whsec_test").unwrap();
+        mac.update(br#"1700000000.{"a":1}"#);
+        assert_eq!(v1, hex(&mac.finalize().into_bytes()));
+
+        assert_ne!(sig, sign_payload("whsec_test", 1_700_000_001, r#"{"a":1}"#));
+        assert_ne!(
+            sig,
+            sign_payload("whsec_other", 1_700_000_000, r#"{"a":1}"#)
+        );
+    }
+
+    #[test]
+    fn backoff_grows_and_caps() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay_after(1), Duration::from_secs(2));
+        assert_eq!(policy.delay_after(2), Duration::from_secs(8));
+        assert_eq!(policy.delay_after(4), Duration::from_secs(128));
+        assert_eq!(policy.delay_after(5), Duration::from_secs(300));
+        assert_eq!(policy.delay_after(40), Duration::from_secs(300));
+    }
+
+    #[tokio::test]
+    async fn endpoint_validation_rejects_private_targets_and_bad_events() {
+        let dispatcher =
+            WebhookDispatcher::with_parts(None, "default", Vec::new(), RetryPolicy::default());
+        for url in [
+            "http://hooks.example.com/x",
+            "https://127.0.0.1/x",
+            "https://localhost/x",
+            "https://user:pw@hooks.example.com/x",
+        ] {
+            assert!(
+                matches!(
+                    dispatcher
+                        .create("x", url, vec![WebhookEventKind::MatterCreated])
+                        .await,
+                    Err(WebhookError::InvalidUrl(_))
+                ),
+                "{url}"
+            );
+        }
+        assert!(matches!(
+            dispatcher
+                .create("x", "https://hooks.example.com/x", Vec::new())
+                .await,
+            Err(WebhookError::NoEvents)
+        ));
+        assert!(matches!(
+            dispatcher
+                .create(
+                    "x",
+                    "https://hooks.example.com/x",
+                    vec![WebhookEventKind::Test]
+                )
+                .await,
+            Err(WebhookError::InvalidEvent(_))
+        ));
+        assert!("conflict.hit".parse::<WebhookEventKind>().is_ok());
+        assert!("webhook.test".parse::<WebhookEventKind>().is_err());
+
+        let created = dispatcher
+            .create(
+                " Docketing ",
+                "https://hooks.example.com/x",
+                vec![WebhookEventKind::DeadlineAdded; 2],
+            )
+            .await
+            .unwrap();
+        assert_eq!(created.name, "Docketing");
+        assert_eq!(created.events, vec![WebhookEventKind::DeadlineAdded]);
+        assert!(created.secret.starts_with("whsec_"));
+        assert!(
+            created
+                .secret_hint()
+                .ends_with(&created.secret[created.secret.len() - 4..])
+        );
+    }
+
+    #[tokio::test]
+    async fn emit_signs_and_retries_server_errors() {
+        let (url, seen) = receiver(2, StatusCode::SERVICE_UNAVAILABLE).await;
+        let dispatcher = Arc::new(WebhookDispatcher::for_tests(None, fast_policy()));
+        let endpoint = dispatcher
+            .create("ops", &url, vec![WebhookEventKind::MatterCreated])
+            .await
+            .unwrap();
+
+        dispatcher.emit(
+            WebhookEventKind::DeadlineAdded,
+            serde_json::json!({ "ignored": true }),
+        );
+        dispatcher.emit(
+            WebhookEventKind::MatterCreated,
+            serde_json::json!({ "matter_id": "acme-v-foo" }),
+        );
+
+        let delivery = wait_for_final(&dispatcher, endpoint.id).await;
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Delivered);
+        assert_eq!(delivery.attempts, 3);
+        assert_eq!(delivery.response_status, Some(200));
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 3, "only the subscribed event is delivered");
+        let (headers, body) = &seen[2];
+        let payload: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(payload["event"], "matter.created");
+        assert_eq!(payload["data"]["matter_id"], "acme-v-foo");
+        assert_eq!(headers[EVENT_HEADER], "matter.created");
+        assert_eq!(headers[DELIVERY_HEADER], delivery.id.to_string());
+
+        let signature = headers[SIGNATURE_HEADER].to_str().unwrap();
+        let t: i64 = signature
+            .strip_prefix("t=")
+            .and_then(|s| s.split(',').next())
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(signature, sign_payload(&endpoint.secret, t, body));
+    }
+
+    #[tokio::test]
+    async fn bus_events_reach_subscribed_endpoints() {
+        let (url, seen) = receiver(0, StatusCode::OK).await;
+        let dispatcher = Arc::new(WebhookDispatcher::for_tests(None, fast_policy()));
+        let endpoint = dispatcher
+            .create("docs", &url, vec![WebhookEventKind::DocumentGenerated])
+            .await
+            .unwrap();
+        let bus = EventBus::new();
+        let _listener = dispatcher.subscribe(&bus);
+
+        bus.publish(DomainEvent::DocumentGenerated {
+            matter_id: "acme-v-foo".to_string(),
+            document_id: None,
+            path: "matters/acme-v-foo/drafts/letter.md".to_string(),
+            template: "letter.md".to_string(),
+            actor: "attorney".to_string(),
+        });
+
+        let delivery = wait_for_final(&dispatcher, endpoint.id).await;
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Delivered);
+        let seen = seen.lock().unwrap();
+        let payload: serde_json::Value = serde_json::from_str(&seen[0].1).unwrap();
+        assert_eq!(payload["event"], "document.generated");
+        assert_eq!(payload["data"]["template"], "letter.md");
+    }
+
+    #[tokio::test]
+    async fn client_errors_fail_without_retry() {
+        let (url, seen) = receiver(usize::MAX, StatusCode::GONE).await;
+        let dispatcher = Arc::new(WebhookDispatcher::for_tests(None, fast_policy()));
+        let endpoint = dispatcher
+            .create("ops", &url, vec![WebhookEventKind::JobCompleted])
+            .await
+            .unwrap();
+
+        dispatcher.emit(WebhookEventKind::JobCompleted, serde_json::json!({}));
+        let delivery = wait_for_final(&dispatcher, endpoint.id).await;
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Failed);
+        assert_eq!(delivery.attempts, 1);
+        assert_eq!(delivery.response_status, Some(410));
+        assert_eq!(seen.lock().unwrap().len(), 1);
+
+        // Disabled endpoints receive nothing.
+        dispatcher
+            .update(
+                endpoint.id,
+                WebhookEndpointUpdate {
+                    enabled: Some(false),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        dispatcher.emit(WebhookEventKind::JobCompleted, serde_json::json!({}));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(seen.lock().unwrap().len(), 1);
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn endpoints_persist_across_reloads() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let dispatcher = WebhookDispatcher::load(Arc::clone(&db), "default").await;
+        let created = dispatcher
+            .create(
+                "ops",
+                "https://hooks.example.com/x",
+                vec![WebhookEventKind::ConflictHit],
+            )
+            .await
+            .unwrap();
+        let rotated = dispatcher
+            .update(
+                created.id,
+                WebhookEndpointUpdate {
+                    rotate_secret: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_ne!(rotated.secret, created.secret);
+
+        let reloaded = WebhookDispatcher::load(Arc::clone(&db), "default").await;
+        assert_eq!(reloaded.list().await, vec![rotated.clone()]);
+
+        reloaded.delete(rotated.id).await.unwrap();
+        let reloaded = WebhookDispatcher::load(db, "default").await;
+        assert!(reloaded.list().await.is_empty());
+    }
+}
@@ -0,0 +1,345 @@
+//! Anonymization pipeline for precedent promotion.
+//!
+//! Before a document joins the precedent bank, [`propose_replacements`]
+//! builds a mapping from client-identifying text to placeholders: the
+//! client's name, the people, organizations, and amounts recorded in the
+//! document entity index (plus a fresh heuristic pass over the current
+//! text), and street addresses. The attorney reviews that before/after
+//! mapping, edits it if needed, and promotes with the [`review_token`] of
+//! the reviewed version, so nothing is published under a mapping nobody
+//! looked at or against a document that changed after review. The token is
+//! an HMAC under a server key and names the reviewer, so a client cannot
+//! mint one without calling the preview endpoint.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use hmac::{Hmac, Mac};
+use regex::Regex;
+use serde::Serialize;
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+
+use crate::db::{
+    CreateDocumentEntityParams, DocumentEntityKind, DocumentEntityRecord, normalize_party_name,
+};
+use crate::legal::entities::{document_entities, extract_entities_heuristic};
+use crate::legal::precedent::{CLIENT_PLACEHOLDER, Replacement, apply_replacements};
+
+/// US-style street addresses: "1200 Market Street, Suite 400, Springfield,
+/// IL 62704". The city is only taken when a state and ZIP follow it.
+static ADDRESS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\b\d{1,6}[ \t]+(?:[A-Z0-9][A-Za-z0-9'\-]*\.?[ \t]+){1,4}(?:(?:Street|Avenue|Road|Boulevard|Lane|Drive|Court|Place|Way|Terrace|Parkway|Highway|Square)\b|(?:St|Ave|Rd|Blvd|Ln|Dr|Ct|Pl|Pkwy|Hwy|Sq)\b\.?)(?:,?[ \t]+(?:Suite|Ste\.?|Unit|Floor|Apt\.?)[ \t]*#?[A-Za-z0-9\-]+)?(?:,[ \t]*[A-Z][A-Za-z.'\-]*(?:[ \t]+[A-Z][A-Za-z.'\-]*){0,3},[ \t]*[A-Z]{2}[ \t]+\d{5}(?:-\d{4})?)?",
+    )
+    .expect("valid address regex")
+});
+
+/// What a proposed replacement hides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnonymizationKind {
+    Client,
+    Organization,
+    Person,
+    Amount,
+    Address,
+}
+
+impl AnonymizationKind {
+    fn from_entity(kind: DocumentEntityKind) -> Option<Self> {
+        match kind {
+            DocumentEntityKind::Organization => Some(Self::Organization),
+            DocumentEntityKind::Person => Some(Self::Person),
+            DocumentEntityKind::Amount => Some(Self::Amount),
+            // Dates rarely identify a client and anchor the document's
+            // chronology, so they stay.
+            DocumentEntityKind::Date => None,
+        }
+    }
+
+    /// Placeholder for the `index`-th (zero-based) value of this kind:
+    /// `[Party A]`, `[Person 1]`, `[Amount 1]`, `[Address 1]`.
+    fn placeholder(self, index: usize) -> String {
+        match self {
+            Self::Client => CLIENT_PLACEHOLDER.to_string(),
+            Self::Organization if index < 26 => {
+                format!("[Party {}]", char::from(b'A' + index as u8))
+            }
+            Self::Organization => format!("[Party {}]", index + 1),
+            Self::Person => format!("[Person {}]", index + 1),
+            Self::Amount => format!("[Amount {}]", index + 1),
+            Self::Address => format!("[Address {}]", index + 1),
+        }
+    }
+}
+
+/// One entry of the before/after mapping shown to the reviewing attorney.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProposedReplacement {
+    /// Text in the source document.
+    pub original: String,
+    /// Text written in its place.
+    pub placeholder: String,
+    pub kind: AnonymizationKind,
+    /// Whole-word matches of `original` in the document.
+    pub occurrences: usize,
+    /// Where the value came from: `client`, `entity_index`, `heuristic`, or
+    /// `address`.
+    pub source: &'static str,
+}
+
+impl From<&ProposedReplacement> for Replacement {
+    fn from(proposed: &ProposedReplacement) -> Self {
+        Self {
+            original: proposed.original.clone(),
+            placeholder: proposed.placeholder.clone(),
+        }
+    }
+}
+
+/// Grouping key: values of one kind that normalize alike share a
+/// placeholder ("Acme Corp." and "ACME Corp").
+fn group_key(kind: AnonymizationKind, value: &str, normalized: &str) -> String {
+    match kind {
+        AnonymizationKind::Amount => normalized.to_string(),
+        AnonymizationKind::Address => value.to_lowercase(),
+        _ => normalize_party_name(value),
+    }
+}
+
+/// Build the proposed mapping for `content`.
+///
+/// `indexed` holds the document entity index rows for the document (other
+/// documents' rows are ignored); they add LLM-extracted names the heuristic
+/// pass would miss. Values naming the client map to `[Client]`; values that
+/// no longer appear in `content` are dropped.
+pub fn propose_replacements(
+    content: &str,
+    source_path: &str,
+    client_name: Option<&str>,
+    indexed: &[DocumentEntityRecord],
+) -> Vec<ProposedReplacement> {
+    let client_key = client_name
+        .map(normalize_party_name)
+        .filter(|key| !key.is_empty());
+
+    let mut candidates: Vec<(AnonymizationKind, String, String, &'static str)> = Vec::new();
+    if let Some(name) = client_name.map(str::trim).filter(|n| !n.is_empty()) {
+        candidates.push((
+            AnonymizationKind::Client,
+            name.to_string(),
+            normalize_party_name(name),
+            "client",
+        ));
+    }
+    for record in indexed.iter().filter(|r| r.source_path == source_path) {
+        if let Some(kind) = AnonymizationKind::from_entity(record.kind) {
+            candidates.push((
+                kind,
+                record.value.clone(),
+                record.normalized_value.clone(),
+                "entity_index",
+            ));
+        }
+    }
+    let fresh: Vec<CreateDocumentEntityParams> =
+        document_entities(content, &extract_entities_heuristic(content));
+    for entity in fresh {
+        if let Some(kind) = AnonymizationKind::from_entity(entity.kind) {
+            candidates.push((kind, entity.value, entity.normalized_value, "heuristic"));
+        }
+    }
+    for m in ADDRESS_RE.find_iter(content) {
+        let value = m.as_str().trim().trim_end_matches(',').to_string();
+        candidates.push((
+            AnonymizationKind::Address,
+            value.clone(),
+            value.to_lowercase(),
+            "address",
+        ));
+    }
+
+    let mut placeholders: HashMap<(AnonymizationKind, String), String> = HashMap::new();
+    let mut counters: HashMap<AnonymizationKind, usize> = HashMap::new();
+    let mut seen_originals: HashSet<String> = HashSet::new();
+    let mut out = Vec::new();
+    for (kind, value, normalized, source) in candidates {
+        let value = value.trim().to_string();
+        if value.is_empty() || seen_originals.contains(&value.to_lowercase()) {
+            continue;
+        }
+        // Party names containing the client's name ("Demo Client LLC")
+        // are the client.
+        let kind = match (kind, client_key.as_deref()) {
+            (AnonymizationKind::Organization | AnonymizationKind::Person, Some(client))
+                if format!(" {} ", normalize_party_name(&value))
+                    .contains(&format!(" {client} ")) =>
+            {
+                AnonymizationKind::Client
+            }
+            _ => kind,
+        };
+        let (_, occurrences) = apply_replacements(
+            content,
+            &[Replacement {
+                original: value.clone(),
+                placeholder: String::new(),
+            }],
+        );
+        if occurrences == 0 {
+            continue;
+        }
+        let key = if kind == AnonymizationKind::Client {
+            String::new()
+        } else {
+            group_key(kind, &value, &normalized)
+        };
+        let placeholder = placeholders
+            .entry((kind, key))
+            .or_insert_with(|| {
+                let counter = counters.entry(kind).or_default();
+                let placeholder = kind.placeholder(*counter);
+                *counter += 1;
+                placeholder
+            })
+            .clone();
+        seen_originals.insert(value.to_lowercase());
+        out.push(ProposedReplacement {
+            original: value,
+            placeholder,
+            kind,
+            occurrences,
+            source,
+        });
+    }
+    out.sort_by_key(|p| p.kind);
+    out
+}
+
+/// HMAC-SHA256 under `key` binding a review to the reviewer, the document
+/// content, and the exact mapping reviewed. Mapping order does not matter.
+pub fn review_token(
+    key: &[u8],
+    reviewer: &str,
+    content: &str,
+    replacements: &[Replacement],
+) -> String {
+    let mut pairs: Vec<(&str, &str)> = replacements
+        .iter()
+        .map(|r| (r.original.trim(), r.placeholder.as_str()))
+        .collect();
+    pairs.sort_unstable();
+    pairs.dedup();
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(reviewer.as_bytes());
+    mac.update(&[0]);
+    mac.update(&(content.len() as u64).to_be_bytes());
+    mac.update(content.as_bytes());
+    for (original, placeholder) in pairs {
+        mac.update(&[0]);
+        mac.update(original.as_bytes());
+        mac.update(&[0]);
+        mac.update(placeholder.as_bytes());
+    }
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Check `token` against [`review_token`] in constant time.
+pub fn review_token_matches(
+    key: &[u8],
+    reviewer: &str,
+    content: &str,
+    replacements: &[Replacement],
+    token: &str,
+) -> bool {
+    review_token(key, reviewer, content, replacements)
+        .as_bytes()
+        .ct_eq(token.as_bytes())
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEASE: &str = "Demo Client LLC (the \"Tenant\") leases from Harbor Point Holdings.\n\n\
+        The Tenant shall pay $12,500 per month to Harbor Point Holdings at \
+        1200 Market Street, Suite 400, Springfield, IL 62704.\n\n\
+        Notices go to Ms. Dana Whitfield.\n";
+
+    #[test]
+    fn proposes_client_party_person_amount_and_address_placeholders() {
+        let proposed =
+            propose_replacements(LEASE, "matters/demo/lease.md", Some("Demo Client"), &[]);
+        let mapping: Vec<(&str, &str, usize)> = proposed
+            .iter()
+            .map(|p| (p.original.as_str(), p.placeholder.as_str(), p.occurrences))
+            .collect();
+        assert_eq!(
+            mapping,
+            vec![
+                ("Demo Client", "[Client]", 1),
+                ("Demo Client LLC", "[Client]", 1),
+                ("Harbor Point Holdings", "[Party A]", 2),
+                ("Dana Whitfield", "[Person 1]", 1),
+                ("$12,500", "[Amount 1]", 1),
+                (
+                    "1200 Market Street, Suite 400, Springfield, IL 62704",
+                    "[Address 1]",
+                    1
+                ),
+            ]
+        );
+
+        let replacements: Vec<Replacement> = proposed.iter().map(Replacement::from).collect();
+        let (out, _) = apply_replacements(LEASE, &replacements);
+        assert!(out.starts_with("[Client] (the \"Tenant\") leases from [Party A]."));
+        assert!(out.contains("pay [Amount 1] per month to [Party A] at [Address 1]."));
+        assert!(out.contains("Notices go to Ms. [Person 1]."));
+    }
+
+    #[test]
+    fn review_token_binds_content_and_mapping_but_not_order() {
+        let a = Replacement {
+            original: "Demo Client".to_string(),
+            placeholder: "[Client]".to_string(),
+        };
+        let b = Replacement {
+            original: "$12,500".to_string(),
+            placeholder: "[Rent]".to_string(),
+        };
+        let key = b"server-key";
+        let token = review_token(key, "alice", LEASE, &[a.clone(), b.clone()]);
+        assert!(review_token_matches(
+            key,
+            "alice",
+            LEASE,
+            &[b.clone(), a.clone()],
+            &token
+        ));
+        assert_ne!(
+            token,
+            review_token(key, "alice", LEASE, std::slice::from_ref(&a))
+        );
+        assert_ne!(
+            token,
+            review_token(key, "alice", "edited", &[a.clone(), b.clone()])
+        );
+        assert_ne!(
+            token,
+            review_token(key, "mallory", LEASE, &[a.clone(), b.clone()])
+        );
+        assert!(!review_token_matches(
+            b"other-key",
+            "alice",
+            LEASE,
+            &[a, b],
+            &token
+        ));
+    }
+}
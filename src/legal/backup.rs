@@ -2269,7 +2269,7 @@ fn build_csv(headers: &[&str], rows: Vec<Vec<String>>) -> String {
     out
 }
 
-fn csv_escape(value: &str) -> String {
+pub(crate) fn csv_escape(value: &str) -> String {
     let mut normalized = value.to_string();
     if matches!(
         normalized.chars().next(),
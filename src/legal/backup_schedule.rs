@@ -0,0 +1,316 @@
+//! Scheduled backups, retention rotation, and the backup directory listing.
+//!
+//! Backups land in [`backups_dir`] as `backup-<timestamp>-<id>.clawyerbak`
+//! bundles produced by [`create_backup_file`], so they carry the same record
+//! set as a manual backup; tables outside it are listed in
+//! `docs/BACKUP_AND_RECOVERY.md`. After each backup the oldest bundles beyond
+//! the retention count are deleted.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::Utc;
+use secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::config::BackupScheduleConfig;
+use crate::db::{AuditSeverity, Database};
+use crate::legal::backup::{
+    BackupCreateOptions, BackupCreateResult, BackupError, BackupManifest, backups_dir,
+    create_backup_file,
+};
+use crate::workspace::Workspace;
+
+const BACKUP_EXTENSION: &str = "clawyerbak";
+
+/// One bundle in the backups directory.
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupListing {
+    pub id: String,
+    pub path: String,
+    pub created_at: String,
+    pub size_bytes: u64,
+    pub user_id: String,
+    pub app_version: String,
+}
+
+/// Outcome of [`backup_now`].
+#[derive(Debug)]
+pub struct BackupRunResult {
+    pub created: BackupCreateResult,
+    /// Ids of bundles deleted by retention rotation.
+    pub rotated: Vec<String>,
+}
+
+/// Unencrypted envelope header; the ciphertext is ignored.
+#[derive(Deserialize)]
+struct EnvelopeHeader {
+    created_at: String,
+    manifest: BackupManifest,
+}
+
+/// Fresh bundle id: `backup-<UTC timestamp>-<uuid>`.
+pub fn new_backup_id() -> String {
+    format!(
+        "backup-{}-{}",
+        Utc::now().format("%Y%m%d-%H%M%S"),
+        Uuid::new_v4().simple()
+    )
+}
+
+/// Bundles in `dir`, newest first. Files whose header cannot be read are
+/// skipped with a warning; a missing directory lists as empty.
+pub async fn list_backups(dir: &Path) -> Result<Vec<BackupListing>, BackupError> {
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(BackupError::Io(e.to_string())),
+    };
+    let mut listings = Vec::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|e| BackupError::Io(e.to_string()))?
+    {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(BACKUP_EXTENSION) {
+            continue;
+        }
+        let Some(id) = path.file_stem().and_then(|s| s.to_str()).map(String::from) else {
+            continue;
+        };
+        let bytes = match tokio::fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Skipping unreadable backup");
+                continue;
+            }
+        };
+        let header: EnvelopeHeader = match serde_json::from_slice(&bytes) {
+            Ok(header) => header,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Skipping malformed backup");
+                continue;
+            }
+        };
+        listings.push(BackupListing {
+            id,
+            path: path.to_string_lossy().to_string(),
+            created_at: header.created_at,
+            size_bytes: bytes.len() as u64,
+            user_id: header.manifest.user_id,
+            app_version: header.manifest.app_version,
+        });
+    }
+    listings.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+    Ok(listings)
+}
+
+/// Delete all but the newest `keep` bundles in `dir`; `keep == 0` deletes
+/// nothing. Returns the ids removed.
+pub async fn rotate_backups(dir: &Path, keep: usize) -> Result<Vec<String>, BackupError> {
+    if keep == 0 {
+        return Ok(Vec::new());
+    }
+    let mut removed = Vec::new();
+    for listing in list_backups(dir).await?.into_iter().skip(keep) {
+        tokio::fs::remove_file(&listing.path)
+            .await
+            .map_err(|e| BackupError::Io(format!("failed to remove {}: {e}", listing.path)))?;
+        removed.push(listing.id);
+    }
+    Ok(removed)
+}
+
+/// Write a new bundle into [`backups_dir`] and apply retention.
+pub async fn backup_now(
+    db: &dyn Database,
+    workspace: &Workspace,
+    user_id: &str,
+    master_key: &SecretString,
+    options: &BackupCreateOptions,
+    retention_count: usize,
+) -> Result<BackupRunResult, BackupError> {
+    let dir = backups_dir()?;
+    let output = dir.join(format!("{}.{BACKUP_EXTENSION}", new_backup_id()));
+    let created = create_backup_file(db, workspace, user_id, &output, master_key, options).await?;
+    let rotated = rotate_backups(&dir, retention_count).await?;
+    Ok(BackupRunResult { created, rotated })
+}
+
+/// Run [`backup_now`] every configured interval, first one interval after
+/// startup. Returns `None` (no task) when scheduling is disabled.
+pub fn spawn_backup_scheduler(
+    db: Arc<dyn Database>,
+    workspace: Arc<Workspace>,
+    user_id: String,
+    master_key: SecretString,
+    matter_root: String,
+    config: &BackupScheduleConfig,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let interval = config.interval()?;
+    let retention_count = config.retention_count;
+    let options = BackupCreateOptions {
+        include_ai_packets: config.include_ai_packets,
+        matter_root,
+    };
+    Some(tokio::spawn(async move {
+        let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            run_scheduled_backup(
+                db.as_ref(),
+                workspace.as_ref(),
+                &user_id,
+                &master_key,
+                &options,
+                retention_count,
+            )
+            .await;
+        }
+    }))
+}
+
+async fn run_scheduled_backup(
+    db: &dyn Database,
+    workspace: &Workspace,
+    user_id: &str,
+    master_key: &SecretString,
+    options: &BackupCreateOptions,
+    retention_count: usize,
+) {
+    match backup_now(db, workspace, user_id, master_key, options, retention_count).await {
+        Ok(result) => {
+            tracing::info!(
+                backup_id = %result.created.artifact.id,
+                size_bytes = result.created.artifact.size_bytes,
+                rotated = result.rotated.len(),
+                "Scheduled backup created"
+            );
+            crate::legal::audit::record_with_db(
+                "backup_created",
+                "scheduler",
+                None,
+                AuditSeverity::Info,
+                serde_json::json!({
+                    "backup_id": result.created.artifact.id,
+                    "path": result.created.artifact.path,
+                    "size_bytes": result.created.artifact.size_bytes,
+                    "scheduled": true,
+                    "rotated": result.rotated,
+                }),
+                db,
+                user_id,
+            )
+            .await;
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Scheduled backup failed");
+            crate::legal::audit::record_with_db(
+                "backup_failed",
+                "scheduler",
+                None,
+                AuditSeverity::Critical,
+                serde_json::json!({ "error": e.to_string(), "scheduled": true }),
+                db,
+                user_id,
+            )
+            .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn write_bundle(dir: &Path, id: &str, created_at: &str) {
+        let body = serde_json::json!({
+            "format": "clawyer-backup",
+            "format_version": 1,
+            "created_at": created_at,
+            "manifest": {
+                "format_version": 1,
+                "schema_version": 2,
+                "app_version": "0.1.0",
+                "created_at": created_at,
+                "user_id": "default",
+                "encrypted": true,
+                "hash_algorithm": "sha256",
+                "section_checksums": {},
+                "notes": [],
+            },
+            "encryption": {},
+            "ciphertext_b64": "",
+        });
+        tokio::fs::write(
+            dir.join(format!("{id}.{BACKUP_EXTENSION}")),
+            serde_json::to_vec(&body).expect("json"),
+        )
+        .await
+        .expect("write bundle");
+    }
+
+    #[tokio::test]
+    async fn listing_is_newest_first_and_skips_foreign_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        write_bundle(dir.path(), "backup-a", "2026-01-01T00:00:00Z").await;
+        write_bundle(dir.path(), "backup-b", "2026-03-01T00:00:00Z").await;
+        tokio::fs::write(dir.path().join("notes.txt"), b"x")
+            .await
+            .expect("write");
+        tokio::fs::write(dir.path().join("broken.clawyerbak"), b"{")
+            .await
+            .expect("write");
+
+        let listings = list_backups(dir.path()).await.expect("list");
+        let ids: Vec<_> = listings.iter().map(|l| l.id.as_str()).collect();
+        assert_eq!(ids, ["backup-b", "backup-a"]);
+        assert_eq!(listings[0].user_id, "default");
+
+        let missing = list_backups(&dir.path().join("absent"))
+            .await
+            .expect("list");
+        assert!(missing.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rotation_keeps_the_newest_bundles() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for (id, ts) in [
+            ("backup-1", "2026-01-01T00:00:00Z"),
+            ("backup-2", "2026-01-02T00:00:00Z"),
+            ("backup-3", "2026-01-03T00:00:00Z"),
+        ] {
+            write_bundle(dir.path(), id, ts).await;
+        }
+
+        assert!(
+            rotate_backups(dir.path(), 0)
+                .await
+                .expect("noop")
+                .is_empty()
+        );
+        let removed = rotate_backups(dir.path(), 2).await.expect("rotate");
+        assert_eq!(removed, ["backup-1"]);
+        let remaining: Vec<_> = list_backups(dir.path())
+            .await
+            .expect("list")
+            .into_iter()
+            .map(|l| l.id)
+            .collect();
+        assert_eq!(remaining, ["backup-3", "backup-2"]);
+    }
+
+    #[test]
+    fn backup_ids_are_path_safe() {
+        let id = new_backup_id();
+        assert!(id.starts_with("backup-"));
+        assert!(
+            id.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-'))
+        );
+    }
+}
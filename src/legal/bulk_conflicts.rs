@@ -0,0 +1,188 @@
+//! Bulk conflict screening.
+//!
+//! A lateral hire brings a list of prior clients that can run to hundreds of
+//! names, well past what the intake conflict check accepts. Each name is run
+//! through the conflict engine on its own so hits stay attributed to the name
+//! that produced them, a batch of names at a time.
+
+use crate::db::{ConflictHit, Database};
+use crate::error::DatabaseError;
+use crate::legal::backup::csv_escape;
+
+/// Names screened concurrently before the next batch starts.
+pub const BULK_CONFLICT_BATCH_SIZE: usize = 25;
+
+/// Header cells recognized as the name column of an uploaded CSV.
+const NAME_COLUMN_HEADERS: [&str; 5] = ["name", "party", "party_name", "client", "client_name"];
+
+/// Conflict hits for one screened name.
+#[derive(Debug, Clone)]
+pub struct BulkConflictRow {
+    pub name: String,
+    pub hits: Vec<ConflictHit>,
+}
+
+/// Read party names from a CSV upload.
+///
+/// A header row naming a `name`/`party`/`client` column selects that column
+/// (case-insensitive, with spaces or hyphens read as underscores);
+/// otherwise the first column of every row is a name.
+pub fn parse_names_csv(body: &str) -> Result<Vec<String>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(body.as_bytes());
+    let mut records = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        records.push(record.map_err(|err| format!("invalid CSV row {}: {err}", index + 1))?);
+    }
+    let header_column = records.first().and_then(|first| {
+        first.iter().position(|cell| {
+            let cell = cell.replace([' ', '-'], "_");
+            NAME_COLUMN_HEADERS
+                .iter()
+                .any(|header| cell.eq_ignore_ascii_case(header))
+        })
+    });
+    let (column, skip) = match header_column {
+        Some(column) => (column, 1),
+        None => (0, 0),
+    };
+    Ok(records
+        .iter()
+        .skip(skip)
+        .filter_map(|record| record.get(column))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Trim names and drop blanks and case-insensitive duplicates, keeping the
+/// first spelling seen.
+pub fn dedupe_names(names: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    names
+        .into_iter()
+        .map(|name| name.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|name| !name.is_empty() && seen.insert(name.to_lowercase()))
+        .collect()
+}
+
+/// Run every name through the conflict engine, `BULK_CONFLICT_BATCH_SIZE`
+/// at a time, keeping at most `hits_per_name` hits for each.
+pub async fn screen_names(
+    store: &dyn Database,
+    names: &[String],
+    hits_per_name: usize,
+) -> Result<Vec<BulkConflictRow>, DatabaseError> {
+    let mut rows = Vec::with_capacity(names.len());
+    for batch in names.chunks(BULK_CONFLICT_BATCH_SIZE) {
+        let checks = batch.iter().map(|name| async move {
+            store
+                .find_conflict_hits_for_names(std::slice::from_ref(name), hits_per_name)
+                .await
+                .map(|hits| BulkConflictRow {
+                    name: name.clone(),
+                    hits,
+                })
+        });
+        for row in futures::future::join_all(checks).await {
+            rows.push(row?);
+        }
+    }
+    Ok(rows)
+}
+
+/// Downloadable CSV report: one line per hit, or a single `clear` line for a
+/// name with no hits.
+pub fn report_csv(rows: &[BulkConflictRow]) -> String {
+    let mut out = String::from(
+        "name,status,party,role,matter_id,matter_status,matched_via,clearance_status\n",
+    );
+    for row in rows {
+        if row.hits.is_empty() {
+            out.push_str(&csv_escape(&row.name));
+            out.push_str(",clear,,,,,,\n");
+            continue;
+        }
+        for hit in &row.hits {
+            let cells = [
+                row.name.as_str(),
+                "potential_conflict",
+                hit.party.as_str(),
+                hit.role.as_str(),
+                hit.matter_id.as_str(),
+                hit.matter_status.as_str(),
+                hit.matched_via.as_str(),
+                hit.clearance_status.as_str(),
+            ];
+            out.push_str(
+                &cells
+                    .iter()
+                    .map(|cell| csv_escape(cell))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{ConflictClearanceStatus, PartyRole};
+
+    #[test]
+    fn csv_uses_named_column_or_first_column() {
+        let named = parse_names_csv("matter,Client Name\nM-1,Acme Corp\nM-2,\"Globex, Inc.\"\n")
+            .expect("named column");
+        assert_eq!(named, vec!["Acme Corp", "Globex, Inc."]);
+
+        let bare = parse_names_csv("Acme Corp\nInitech,former\n").expect("first column");
+        assert_eq!(bare, vec!["Acme Corp", "Initech"]);
+    }
+
+    #[test]
+    fn dedupe_collapses_case_and_whitespace() {
+        let names = dedupe_names(vec![
+            " Acme  Corp ".to_string(),
+            "acme corp".to_string(),
+            String::new(),
+            "Initech".to_string(),
+        ]);
+        assert_eq!(names, vec!["Acme Corp", "Initech"]);
+    }
+
+    #[test]
+    fn report_lists_each_hit_and_clear_names() {
+        let rows = vec![
+            BulkConflictRow {
+                name: "Acme Corp".to_string(),
+                hits: vec![ConflictHit {
+                    party: "Acme Corporation".to_string(),
+                    role: PartyRole::Adverse,
+                    matter_id: "globex-v-acme".to_string(),
+                    matter_status: "Open".to_string(),
+                    matched_via: "alias:acme corp".to_string(),
+                    relationship_path: Vec::new(),
+                    clearance_status: ConflictClearanceStatus::Unreviewed,
+                    latest_clearance: None,
+                }],
+            },
+            BulkConflictRow {
+                name: "=Initech".to_string(),
+                hits: Vec::new(),
+            },
+        ];
+        let report = report_csv(&rows);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[1],
+            "Acme Corp,potential_conflict,Acme Corporation,adverse,globex-v-acme,Open,alias:acme corp,unreviewed"
+        );
+        assert_eq!(lines[2], "'=Initech,clear,,,,,,");
+    }
+}
@@ -4,6 +4,8 @@
 //! - Calendar-day periods: adds the offset and pushes the last day past
 //!   any weekend or jurisdiction-specific court holiday to the next business day.
 //! - Court-day periods: counts only business days (Mon–Fri, non-holiday).
+//! - Month terms (`offset_months`, used by the IP rules in `ip_rules.toml`)
+//!   are added before the day offset, clamping to the end of a shorter month.
 //!
 //! All computation produces a human-readable [`ComputationTrace`] that can
 //! be stored alongside the deadline for attorney review and audit.
@@ -12,11 +14,12 @@
 //!
 //! [`DeadlineProvider`] is a trait so a vendor adapter (CompuLaw, CourtRule,
 //! etc.) can be plugged in later without changing handler code.  The default
-//! implementation [`FirstPartyProvider`] is backed by `court_rules.toml`.
+//! implementation [`FirstPartyProvider`] is backed by `court_rules.toml` and
+//! `ip_rules.toml`.
 
 use std::sync::LazyLock;
 
-use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, Utc, Weekday};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -56,6 +59,9 @@ pub struct ComputationTrace {
     pub engine_version: String,
     /// ISO-8601 date of the event that started the period.
     pub trigger_date: String,
+    /// Whole months added before the day count.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub offset_months: i64,
     /// Number of days in the period (positive = forward).
     pub offset_days: i64,
     /// Whether court days (true) or calendar days (false) were counted.
@@ -77,6 +83,10 @@ pub struct CourtRule {
     pub id: String,
     pub citation: String,
     pub deadline_type: MatterDeadlineType,
+    /// Whole calendar months added before `offset_days` (IP terms such as
+    /// "3 months from mailing" or "10 years from registration"). A day past
+    /// the end of the target month clamps to its last day.
+    pub offset_months: i64,
     pub offset_days: i64,
     pub court_days: bool,
     /// Rule version string (sourced from TOML; defaults to `"1"`).
@@ -99,6 +109,9 @@ struct RawCourtRule {
     id: String,
     citation: String,
     deadline_type: String,
+    #[serde(default)]
+    offset_months: i64,
+    #[serde(default)]
     offset_days: i64,
     #[serde(default)]
     court_days: bool,
@@ -115,8 +128,15 @@ fn default_jurisdiction() -> String {
     "FRCP".to_string()
 }
 
-static COURT_RULES: LazyLock<Result<Vec<CourtRule>, String>> =
-    LazyLock::new(|| parse_rules(include_str!("court_rules.toml")));
+fn is_zero(value: &i64) -> bool {
+    *value == 0
+}
+
+static COURT_RULES: LazyLock<Result<Vec<CourtRule>, String>> = LazyLock::new(|| {
+    let mut rules = parse_rules(include_str!("court_rules.toml"))?;
+    rules.extend(parse_rules(include_str!("ip_rules.toml"))?);
+    Ok(rules)
+});
 
 fn parse_rules(raw: &str) -> Result<Vec<CourtRule>, String> {
     let parsed: CourtRuleConfig =
@@ -134,6 +154,7 @@ fn parse_rules(raw: &str) -> Result<Vec<CourtRule>, String> {
             id: rule.id,
             citation: rule.citation,
             deadline_type,
+            offset_months: rule.offset_months,
             offset_days: rule.offset_days,
             court_days: rule.court_days,
             version: rule.version,
@@ -260,6 +281,9 @@ fn is_court_holiday(date: NaiveDate, jurisdiction: &str) -> bool {
         "ON" => ontario_court_holidays(date.year())
             .binary_search(&date)
             .is_ok(),
+        // Closure days of non-U.S. IP offices are not modelled; only
+        // weekends roll the deadline forward.
+        "EUIPO" | "EPO" | "UKIPO" | "CIPO" | "WIPO" => false,
         _ => is_federal_holiday(date),
     }
 }
@@ -295,6 +319,7 @@ fn weekday_name(wd: Weekday) -> &'static str {
 /// produce a human-readable [`ComputationTrace`].
 ///
 /// Implements FRCP 6(a)(1):
+/// - Month terms: add `offset_months` first, clamping to the month end.
 /// - Calendar days: add `offset_days`; if that day is a weekend or federal
 ///   holiday, advance to the next business day.
 /// - Court days: count only business days (Mon–Fri, non-holiday).
@@ -322,15 +347,43 @@ pub fn apply_rule_with_trace(
         trigger_naive.format("%A, %B %e, %Y")
     ));
 
-    let result_naive = if !rule.court_days {
-        // --- Calendar-day period ---
+    // Month terms are applied first, clamping to the end of a shorter month.
+    let start = if rule.offset_months == 0 {
+        trigger_naive
+    } else {
+        let months = Months::new(u32::try_from(rule.offset_months.unsigned_abs()).unwrap_or(0));
+        let moved = if rule.offset_months > 0 {
+            trigger_naive.checked_add_months(months)
+        } else {
+            trigger_naive.checked_sub_months(months)
+        }
+        .unwrap_or(trigger_naive);
         push!(format!(
-            "Add {} calendar {} per {} (court_days = false)",
-            rule.offset_days,
-            if rule.offset_days == 1 { "day" } else { "days" },
-            rule.citation
+            "Add {} calendar {} per {}: {} ({})",
+            rule.offset_months,
+            if rule.offset_months == 1 {
+                "month"
+            } else {
+                "months"
+            },
+            rule.citation,
+            moved,
+            moved.format("%A, %B %e, %Y")
         ));
-        let raw = trigger_naive + Duration::days(rule.offset_days);
+        moved
+    };
+
+    let result_naive = if !rule.court_days {
+        // --- Calendar-day period ---
+        if rule.offset_days != 0 || rule.offset_months == 0 {
+            push!(format!(
+                "Add {} calendar {} per {} (court_days = false)",
+                rule.offset_days,
+                if rule.offset_days == 1 { "day" } else { "days" },
+                rule.citation
+            ));
+        }
+        let raw = start + Duration::days(rule.offset_days);
         push!(format!(
             "Raw result: {} ({})",
             raw,
@@ -376,7 +429,7 @@ pub fn apply_rule_with_trace(
 
         let step_dir: i64 = if rule.offset_days >= 0 { 1 } else { -1 };
         let mut remaining = rule.offset_days.unsigned_abs();
-        let mut cursor = trigger_naive;
+        let mut cursor = start;
 
         while remaining > 0 {
             cursor += Duration::days(step_dir);
@@ -419,6 +472,7 @@ pub fn apply_rule_with_trace(
         rule_version: rule.version.clone(),
         engine_version: ENGINE_VERSION.to_string(),
         trigger_date: trigger_naive.to_string(),
+        offset_months: rule.offset_months,
         offset_days: rule.offset_days,
         court_days: rule.court_days,
         steps,
@@ -674,6 +728,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn uscis_presets_add_mailing_days_and_open_filing_windows_early() {
+        let trigger = chrono::Utc
+            .with_ymd_and_hms(2026, 3, 2, 10, 0, 0)
+            .single()
+            .expect("valid trigger");
+        let rfe = get_court_rule("uscis_rfe_response")
+            .expect("rules should parse")
+            .expect("rule should exist");
+        // 84 days plus 3 mailing days: Mon 2026-03-02 → Thu 2026-05-28
+        assert_eq!(
+            apply_rule(&rfe, trigger).date_naive().to_string(),
+            "2026-05-28"
+        );
+
+        let early = get_court_rule("uscis_n400_early_filing")
+            .expect("rules should parse")
+            .expect("rule should exist");
+        let eligible = chrono::Utc
+            .with_ymd_and_hms(2026, 6, 15, 10, 0, 0)
+            .single()
+            .expect("valid trigger");
+        assert_eq!(
+            apply_rule(&early, eligible).date_naive().to_string(),
+            "2026-03-17"
+        );
+    }
+
     // ---- court-day computation ----
 
     #[test]
@@ -682,6 +764,7 @@ mod tests {
             id: "test".to_string(),
             citation: "Test Rule".to_string(),
             deadline_type: MatterDeadlineType::Internal,
+            offset_months: 0,
             offset_days: 3,
             court_days: true,
             version: "1".to_string(),
@@ -765,6 +848,7 @@ mod tests {
             id: "test_rule".to_string(),
             citation: "Test Rule".to_string(),
             deadline_type: MatterDeadlineType::Internal,
+            offset_months: 0,
             offset_days: 1,
             court_days: false,
             version: "1".to_string(),
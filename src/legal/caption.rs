@@ -0,0 +1,652 @@
+//! Court captions, signature blocks, and certificates of service.
+//!
+//! A [`CaptionProfile`] is resolved from the matter's jurisdiction (and an
+//! optional court override) and decides the layout: U.S. federal and state
+//! courts use the `v.` caption with a case number, Ontario uses the
+//! `BETWEEN: ... - and -` form with a court file number. Anything the matter
+//! does not record is left as a bracketed placeholder to fill in.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::db::{ClientRecord, MatterPartyRecord, MatterRecord, PartyRole};
+
+/// Caption layout family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptionStyle {
+    UsFederal,
+    UsState,
+    Ontario,
+    Generic,
+}
+
+impl CaptionStyle {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::UsFederal => "us_federal",
+            Self::UsState => "us_state",
+            Self::Ontario => "ontario",
+            Self::Generic => "generic",
+        }
+    }
+}
+
+/// Formatting profile for one court.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaptionProfile {
+    pub style: CaptionStyle,
+    /// Court heading, one line per entry.
+    pub court_heading: Vec<String>,
+    pub case_number_label: String,
+}
+
+/// Which side of the `v.` the firm's client is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientSide {
+    #[default]
+    Plaintiff,
+    Defendant,
+}
+
+impl ClientSide {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "plaintiff" | "petitioner" | "applicant" => Some(Self::Plaintiff),
+            "defendant" | "respondent" => Some(Self::Defendant),
+            _ => None,
+        }
+    }
+}
+
+/// Parties and case number for a caption.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaptionParties {
+    pub plaintiffs: Vec<String>,
+    pub defendants: Vec<String>,
+    pub case_number: Option<String>,
+    #[serde(default)]
+    pub client_side: ClientSide,
+}
+
+/// Attorney signing the document.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaptionSigner {
+    pub name: String,
+    #[serde(default)]
+    pub bar_number: Option<String>,
+    #[serde(default)]
+    pub firm: Option<String>,
+    /// Street address; line breaks are kept.
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub phone: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+/// One person served with the document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceEntry {
+    pub name: String,
+    /// e.g. `CM/ECF`, `email`, `first-class mail`.
+    pub method: String,
+    #[serde(default)]
+    pub address: Option<String>,
+}
+
+const FEDERAL_DISTRICTS: &[(&str, &str)] = &[
+    ("SDNY", "SOUTHERN DISTRICT OF NEW YORK"),
+    ("EDNY", "EASTERN DISTRICT OF NEW YORK"),
+    ("NDNY", "NORTHERN DISTRICT OF NEW YORK"),
+    ("WDNY", "WESTERN DISTRICT OF NEW YORK"),
+    ("NDCAL", "NORTHERN DISTRICT OF CALIFORNIA"),
+    ("CDCAL", "CENTRAL DISTRICT OF CALIFORNIA"),
+    ("CDCA", "CENTRAL DISTRICT OF CALIFORNIA"),
+    ("SDCAL", "SOUTHERN DISTRICT OF CALIFORNIA"),
+    ("EDCAL", "EASTERN DISTRICT OF CALIFORNIA"),
+    ("NDILL", "NORTHERN DISTRICT OF ILLINOIS"),
+    ("NDTEX", "NORTHERN DISTRICT OF TEXAS"),
+    ("SDTEX", "SOUTHERN DISTRICT OF TEXAS"),
+    ("EDTEX", "EASTERN DISTRICT OF TEXAS"),
+    ("WDTEX", "WESTERN DISTRICT OF TEXAS"),
+    ("SDFLA", "SOUTHERN DISTRICT OF FLORIDA"),
+    ("MDFLA", "MIDDLE DISTRICT OF FLORIDA"),
+    ("EDVA", "EASTERN DISTRICT OF VIRGINIA"),
+    ("EDPA", "EASTERN DISTRICT OF PENNSYLVANIA"),
+    ("DNJ", "DISTRICT OF NEW JERSEY"),
+    ("DDEL", "DISTRICT OF DELAWARE"),
+    ("DMASS", "DISTRICT OF MASSACHUSETTS"),
+    ("DDC", "DISTRICT OF COLUMBIA"),
+];
+
+const US_STATES: &[(&str, &str)] = &[
+    ("AL", "ALABAMA"),
+    ("AK", "ALASKA"),
+    ("AZ", "ARIZONA"),
+    ("AR", "ARKANSAS"),
+    ("CA", "CALIFORNIA"),
+    ("CO", "COLORADO"),
+    ("CT", "CONNECTICUT"),
+    ("DE", "DELAWARE"),
+    ("DC", "DISTRICT OF COLUMBIA"),
+    ("FL", "FLORIDA"),
+    ("GA", "GEORGIA"),
+    ("HI", "HAWAII"),
+    ("ID", "IDAHO"),
+    ("IL", "ILLINOIS"),
+    ("IN", "INDIANA"),
+    ("IA", "IOWA"),
+    ("KS", "KANSAS"),
+    ("KY", "KENTUCKY"),
+    ("LA", "LOUISIANA"),
+    ("ME", "MAINE"),
+    ("MD", "MARYLAND"),
+    ("MA", "MASSACHUSETTS"),
+    ("MI", "MICHIGAN"),
+    ("MN", "MINNESOTA"),
+    ("MS", "MISSISSIPPI"),
+    ("MO", "MISSOURI"),
+    ("MT", "MONTANA"),
+    ("NE", "NEBRASKA"),
+    ("NV", "NEVADA"),
+    ("NH", "NEW HAMPSHIRE"),
+    ("NJ", "NEW JERSEY"),
+    ("NM", "NEW MEXICO"),
+    ("NY", "NEW YORK"),
+    ("NC", "NORTH CAROLINA"),
+    ("ND", "NORTH DAKOTA"),
+    ("OH", "OHIO"),
+    ("OK", "OKLAHOMA"),
+    ("OR", "OREGON"),
+    ("PA", "PENNSYLVANIA"),
+    ("RI", "RHODE ISLAND"),
+    ("SC", "SOUTH CAROLINA"),
+    ("SD", "SOUTH DAKOTA"),
+    ("TN", "TENNESSEE"),
+    ("TX", "TEXAS"),
+    ("UT", "UTAH"),
+    ("VT", "VERMONT"),
+    ("VA", "VIRGINIA"),
+    ("WA", "WASHINGTON"),
+    ("WV", "WEST VIRGINIA"),
+    ("WI", "WISCONSIN"),
+    ("WY", "WYOMING"),
+];
+
+/// Resolve the formatting profile for `jurisdiction` (`SDNY`, `FRCP`, `NY`,
+/// `ON`, ...). `court` replaces the default heading when given.
+pub fn caption_profile(jurisdiction: Option<&str>, court: Option<&str>) -> CaptionProfile {
+    let code: String = jurisdiction
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_uppercase();
+    let court_lines = court
+        .map(|court| {
+            court
+                .lines()
+                .map(|line| line.trim().to_uppercase())
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .filter(|lines| !lines.is_empty());
+
+    let (style, default_heading, case_number_label) = if code == "ON" || code == "ONTARIO" {
+        (
+            CaptionStyle::Ontario,
+            vec![
+                "ONTARIO".to_string(),
+                "SUPERIOR COURT OF JUSTICE".to_string(),
+            ],
+            "Court File No.",
+        )
+    } else if let Some((_, district)) = FEDERAL_DISTRICTS.iter().find(|(key, _)| *key == code) {
+        (
+            CaptionStyle::UsFederal,
+            vec![
+                "UNITED STATES DISTRICT COURT".to_string(),
+                (*district).to_string(),
+            ],
+            "Case No.",
+        )
+    } else if code == "FRCP" || code == "FEDERAL" || code == "US" {
+        (
+            CaptionStyle::UsFederal,
+            vec![
+                "UNITED STATES DISTRICT COURT".to_string(),
+                "[DISTRICT]".to_string(),
+            ],
+            "Case No.",
+        )
+    } else if let Some((key, state)) = US_STATES.iter().find(|(key, _)| *key == code) {
+        let heading = match *key {
+            "NY" => vec![
+                "SUPREME COURT OF THE STATE OF NEW YORK".to_string(),
+                "COUNTY OF [COUNTY]".to_string(),
+            ],
+            "CA" => vec![
+                "SUPERIOR COURT OF THE STATE OF CALIFORNIA".to_string(),
+                "COUNTY OF [COUNTY]".to_string(),
+            ],
+            _ => vec![format!("IN THE [COURT] OF THE STATE OF {state}")],
+        };
+        let label = if *key == "NY" {
+            "Index No."
+        } else {
+            "Case No."
+        };
+        (CaptionStyle::UsState, heading, label)
+    } else {
+        (
+            CaptionStyle::Generic,
+            vec!["[COURT]".to_string()],
+            "Case No.",
+        )
+    };
+
+    CaptionProfile {
+        style,
+        court_heading: court_lines.unwrap_or(default_heading),
+        case_number_label: case_number_label.to_string(),
+    }
+}
+
+/// First non-empty string among `keys` in the matter's custom fields.
+fn custom_field(matter: &MatterRecord, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| {
+        matter
+            .custom_fields
+            .get(*key)
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    })
+}
+
+/// Court recorded on the matter (`custom_fields.court`), if any.
+pub fn matter_court(matter: &MatterRecord) -> Option<String> {
+    custom_field(matter, &["court"])
+}
+
+/// Caption parties from the matter: the client plus client-role parties on
+/// the client's side, adverse parties on the other. The case number comes
+/// from `custom_fields` (`case_number`, `docket`, `index_number`,
+/// `court_file_number`) and the side from `custom_fields.client_position`
+/// unless `side` is given.
+pub fn caption_parties_from_matter(
+    matter: &MatterRecord,
+    client: &ClientRecord,
+    parties: &[MatterPartyRecord],
+    side: Option<ClientSide>,
+) -> CaptionParties {
+    let side = side
+        .or_else(|| {
+            custom_field(matter, &["client_position"]).and_then(|raw| ClientSide::parse(&raw))
+        })
+        .unwrap_or_default();
+    let mut ours = vec![client.name.clone()];
+    let mut theirs = Vec::new();
+    for party in parties.iter().filter(|party| party.closed_at.is_none()) {
+        let names = match party.role {
+            PartyRole::Client => &mut ours,
+            PartyRole::Adverse => &mut theirs,
+            _ => continue,
+        };
+        if !names
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(&party.name))
+        {
+            names.push(party.name.clone());
+        }
+    }
+    let (plaintiffs, defendants) = match side {
+        ClientSide::Plaintiff => (ours, theirs),
+        ClientSide::Defendant => (theirs, ours),
+    };
+    CaptionParties {
+        plaintiffs,
+        defendants,
+        case_number: custom_field(
+            matter,
+            &["case_number", "docket", "index_number", "court_file_number"],
+        ),
+        client_side: side,
+    }
+}
+
+/// Profile and parties for a matter's caption. `side` overrides the
+/// matter's recorded client position.
+pub fn matter_caption(
+    matter: &MatterRecord,
+    client: &ClientRecord,
+    parties: &[MatterPartyRecord],
+    side: Option<ClientSide>,
+) -> crate::legal::docgen::CaptionContext {
+    crate::legal::docgen::CaptionContext {
+        profile: caption_profile(
+            matter.jurisdiction.as_deref(),
+            matter_court(matter).as_deref(),
+        ),
+        parties: caption_parties_from_matter(matter, client, parties, side),
+    }
+}
+
+fn party_label(singular: &str, count: usize) -> String {
+    if count > 1 {
+        format!("{singular}s")
+    } else {
+        singular.to_string()
+    }
+}
+
+fn party_lines(names: &[String], placeholder: &str) -> Vec<String> {
+    if names.is_empty() {
+        return vec![placeholder.to_string()];
+    }
+    names.iter().map(|name| name.to_uppercase()).collect()
+}
+
+/// Caption block for a document titled `title`.
+pub fn render_caption(profile: &CaptionProfile, parties: &CaptionParties, title: &str) -> String {
+    let case_number = parties
+        .case_number
+        .clone()
+        .unwrap_or_else(|| "[CASE NUMBER]".to_string());
+    let plaintiffs = party_lines(&parties.plaintiffs, "[PLAINTIFF]");
+    let defendants = party_lines(&parties.defendants, "[DEFENDANT]");
+    let plaintiff_label = party_label("Plaintiff", parties.plaintiffs.len());
+    let defendant_label = party_label("Defendant", parties.defendants.len());
+    let title = title.trim().to_uppercase();
+    let mut lines = Vec::new();
+
+    match profile.style {
+        CaptionStyle::Ontario => {
+            lines.push(format!("{} {case_number}", profile.case_number_label));
+            lines.push(String::new());
+            lines.extend(profile.court_heading.iter().cloned());
+            lines.push(String::new());
+            lines.push("BETWEEN:".to_string());
+            lines.push(String::new());
+            lines.extend(plaintiffs);
+            lines.push(format!("{:>60}", plaintiff_label));
+            lines.push(String::new());
+            lines.push(format!("{:^60}", "- and -").trim_end().to_string());
+            lines.push(String::new());
+            lines.extend(defendants);
+            lines.push(format!("{:>60}", defendant_label));
+        }
+        CaptionStyle::UsFederal | CaptionStyle::UsState | CaptionStyle::Generic => {
+            lines.extend(profile.court_heading.iter().cloned());
+            lines.push(String::new());
+            let last = plaintiffs.len() - 1;
+            for (i, name) in plaintiffs.into_iter().enumerate() {
+                lines.push(if i == last { format!("{name},") } else { name });
+            }
+            lines.push(format!("        {plaintiff_label},"));
+            lines.push(String::new());
+            lines.push(format!(
+                "    v.{:>44}",
+                format!("{} {case_number}", profile.case_number_label)
+            ));
+            lines.push(String::new());
+            let last = defendants.len() - 1;
+            for (i, name) in defendants.into_iter().enumerate() {
+                lines.push(if i == last { format!("{name},") } else { name });
+            }
+            lines.push(format!("        {defendant_label}."));
+        }
+    }
+    lines.push(String::new());
+    lines.push(title);
+    lines.join("\n")
+}
+
+fn long_date(date: NaiveDate) -> String {
+    date.format("%B %-d, %Y").to_string()
+}
+
+/// Signature block for `signer`, who represents the client's side.
+pub fn render_signature_block(
+    profile: &CaptionProfile,
+    signer: &CaptionSigner,
+    parties: &CaptionParties,
+    date: NaiveDate,
+) -> String {
+    let (side, names) = match parties.client_side {
+        ClientSide::Plaintiff => ("Plaintiff", &parties.plaintiffs),
+        ClientSide::Defendant => ("Defendant", &parties.defendants),
+    };
+    let side = party_label(side, names.len());
+    let mut lines = Vec::new();
+    let contact = |lines: &mut Vec<String>| {
+        if let Some(ref address) = signer.address {
+            lines.extend(address.lines().map(|line| line.trim().to_string()));
+        }
+        if let Some(ref phone) = signer.phone {
+            lines.push(format!("Tel: {phone}"));
+        }
+        if let Some(ref email) = signer.email {
+            lines.push(format!("Email: {email}"));
+        }
+    };
+
+    match profile.style {
+        CaptionStyle::Ontario => {
+            lines.push(long_date(date));
+            lines.push(String::new());
+            if let Some(ref firm) = signer.firm {
+                lines.push(firm.to_uppercase());
+            }
+            contact(&mut lines);
+            lines.push(String::new());
+            lines.push(match signer.bar_number {
+                Some(ref lso) => format!("{} (LSO #{lso})", signer.name),
+                None => signer.name.clone(),
+            });
+            lines.push(String::new());
+            lines.push(format!("Lawyers for the {side}"));
+        }
+        CaptionStyle::UsFederal | CaptionStyle::UsState | CaptionStyle::Generic => {
+            lines.push(format!("Dated: {}", long_date(date)));
+            lines.push(String::new());
+            lines.push("Respectfully submitted,".to_string());
+            lines.push(String::new());
+            lines.push(format!("/s/ {}", signer.name));
+            lines.push(signer.name.clone());
+            if let Some(ref bar) = signer.bar_number {
+                lines.push(format!("Bar No. {bar}"));
+            }
+            if let Some(ref firm) = signer.firm {
+                lines.push(firm.clone());
+            }
+            contact(&mut lines);
+            let attorneys = if names.is_empty() {
+                format!("Attorneys for {side}")
+            } else {
+                format!(
+                    "Attorneys for {side} {}",
+                    crate::legal::docgen::join_names(names, "and")
+                )
+            };
+            lines.push(String::new());
+            lines.push(attorneys);
+        }
+    }
+    lines.join("\n")
+}
+
+/// Certificate of service (U.S.) or affidavit of service (Ontario) for the
+/// document titled `title`, signed by `signer_name`.
+pub fn render_certificate_of_service(
+    profile: &CaptionProfile,
+    title: &str,
+    served: &[ServiceEntry],
+    date: NaiveDate,
+    signer_name: &str,
+) -> String {
+    let served_lines: Vec<String> = served
+        .iter()
+        .map(|entry| match entry.address {
+            Some(ref address) => format!("{}, {} (by {})", entry.name, address, entry.method),
+            None => format!("{} (by {})", entry.name, entry.method),
+        })
+        .collect();
+    let title = title.trim();
+    let mut lines = Vec::new();
+
+    match profile.style {
+        CaptionStyle::Ontario => {
+            lines.push("AFFIDAVIT OF SERVICE".to_string());
+            lines.push(String::new());
+            lines.push(format!(
+                "I, {signer_name}, of the City of [CITY], in the Province of Ontario, MAKE OATH AND SAY:"
+            ));
+            lines.push(String::new());
+            for (i, served) in served_lines.iter().enumerate() {
+                lines.push(format!(
+                    "{}. On {}, I served {served} with the {title}.",
+                    i + 1,
+                    long_date(date)
+                ));
+            }
+            lines.push(String::new());
+            lines.push(format!(
+                "SWORN BEFORE ME at the City of [CITY], in the Province of Ontario, on {}.",
+                long_date(date)
+            ));
+            lines.push(String::new());
+            lines.push("Commissioner for Taking Affidavits".to_string());
+            lines.push(String::new());
+            lines.push(signer_name.to_string());
+        }
+        CaptionStyle::UsFederal | CaptionStyle::UsState | CaptionStyle::Generic => {
+            lines.push("CERTIFICATE OF SERVICE".to_string());
+            lines.push(String::new());
+            lines.push(format!(
+                "I hereby certify that on {}, I served a true and correct copy of the foregoing {title} on the following:",
+                long_date(date)
+            ));
+            lines.push(String::new());
+            lines.extend(served_lines.iter().map(|line| format!("- {line}")));
+            lines.push(String::new());
+            lines.push(format!("/s/ {signer_name}"));
+            lines.push(signer_name.to_string());
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parties() -> CaptionParties {
+        CaptionParties {
+            plaintiffs: vec!["Acme Corp".to_string()],
+            defendants: vec!["Globex LLC".to_string(), "Initech Inc".to_string()],
+            case_number: Some("24-cv-100".to_string()),
+            client_side: ClientSide::Plaintiff,
+        }
+    }
+
+    #[test]
+    fn profiles_follow_the_jurisdiction() {
+        let sdny = caption_profile(Some("S.D.N.Y."), None);
+        assert_eq!(sdny.style, CaptionStyle::UsFederal);
+        assert_eq!(sdny.court_heading[1], "SOUTHERN DISTRICT OF NEW YORK");
+        let ny = caption_profile(Some("NY"), None);
+        assert_eq!(ny.style, CaptionStyle::UsState);
+        assert_eq!(ny.case_number_label, "Index No.");
+        assert_eq!(
+            caption_profile(Some("ON"), None).style,
+            CaptionStyle::Ontario
+        );
+        let custom = caption_profile(Some("TX"), Some("District Court of Travis County"));
+        assert_eq!(
+            custom.court_heading,
+            vec!["DISTRICT COURT OF TRAVIS COUNTY"]
+        );
+        assert_eq!(caption_profile(None, None).court_heading, vec!["[COURT]"]);
+    }
+
+    #[test]
+    fn us_caption_lists_parties_around_v() {
+        let caption = render_caption(
+            &caption_profile(Some("SDNY"), None),
+            &parties(),
+            "Motion to Dismiss",
+        );
+        assert!(
+            caption.starts_with("UNITED STATES DISTRICT COURT\nSOUTHERN DISTRICT OF NEW YORK\n")
+        );
+        assert!(caption.contains("ACME CORP,\n        Plaintiff,"));
+        assert!(caption.contains("Case No. 24-cv-100"));
+        assert!(caption.contains("GLOBEX LLC\nINITECH INC,\n        Defendants."));
+        assert!(caption.ends_with("\n\nMOTION TO DISMISS"));
+    }
+
+    #[test]
+    fn ontario_caption_and_service_use_court_file_and_affidavit_forms() {
+        let profile = caption_profile(Some("ON"), None);
+        let caption = render_caption(&profile, &parties(), "Statement of Claim");
+        assert!(
+            caption.starts_with("Court File No. 24-cv-100\n\nONTARIO\nSUPERIOR COURT OF JUSTICE")
+        );
+        assert!(caption.contains("BETWEEN:"));
+        assert!(caption.contains("- and -"));
+
+        let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let service = render_certificate_of_service(
+            &profile,
+            "Statement of Claim",
+            &[ServiceEntry {
+                name: "Globex LLC".to_string(),
+                method: "email".to_string(),
+                address: None,
+            }],
+            date,
+            "Jane Doe",
+        );
+        assert!(service.starts_with("AFFIDAVIT OF SERVICE"));
+        assert!(service.contains("1. On March 2, 2026, I served Globex LLC (by email)"));
+
+        let signature = render_signature_block(
+            &profile,
+            &CaptionSigner {
+                name: "Jane Doe".to_string(),
+                bar_number: Some("12345A".to_string()),
+                firm: Some("Doe LLP".to_string()),
+                ..CaptionSigner::default()
+            },
+            &parties(),
+            date,
+        );
+        assert!(signature.contains("Jane Doe (LSO #12345A)"));
+        assert!(signature.ends_with("Lawyers for the Plaintiff"));
+    }
+
+    #[test]
+    fn us_signature_names_the_represented_parties() {
+        let mut parties = parties();
+        parties.client_side = ClientSide::Defendant;
+        let signature = render_signature_block(
+            &caption_profile(Some("SDNY"), None),
+            &CaptionSigner {
+                name: "Jane Doe".to_string(),
+                bar_number: Some("JD1234".to_string()),
+                email: Some("jane@doe.test".to_string()),
+                ..CaptionSigner::default()
+            },
+            &parties,
+            NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+        );
+        assert!(signature.starts_with("Dated: March 2, 2026"));
+        assert!(signature.contains("/s/ Jane Doe\nJane Doe\nBar No. JD1234\nEmail: jane@doe.test"));
+        assert!(signature.ends_with("Attorneys for Defendants Globex LLC and Initech Inc"));
+    }
+}
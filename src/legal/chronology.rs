@@ -0,0 +1,614 @@
+//! Matter chronology built from dated facts.
+//!
+//! Facts are extracted from matter documents (a deterministic date scan,
+//! optionally merged with LLM extraction), stored with their source path
+//! and line in the fact store, and rendered into the matter's
+//! `facts/key_facts.md` and `templates/chronology.md` tables. Those files
+//! are regenerated from the store on every extraction, so the structured
+//! rows are the source of truth rather than hand-edited markdown.
+
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+use chrono::NaiveDate;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::db::MatterFactRecord;
+use crate::legal::markdown::table_cell;
+use crate::llm::{ChatMessage, CompletionRequest, LlmProvider, LlmTask};
+
+/// Characters of document content sent to the LLM extractor.
+const LLM_EXCERPT_CHARS: usize = 8_000;
+
+/// Upper bound on facts stored from a single document.
+pub const MAX_FACTS_PER_DOCUMENT: usize = 100;
+
+const DESCRIPTION_CHARS: usize = 240;
+const EXCERPT_CHARS: usize = 240;
+
+const MONTHS: &str = "January|February|March|April|May|June|July|August|September|October|November|December|Jan|Feb|Mar|Apr|Jun|Jul|Aug|Sept|Sep|Oct|Nov|Dec";
+
+/// "March 3, 2024", "Mar. 3rd 2024".
+static MONTH_DAY_YEAR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"\b({MONTHS})\.?[ \t]+(\d{{1,2}})(?:st|nd|rd|th)?,?[ \t]+(\d{{4}})\b"
+    ))
+    .expect("valid month-day-year regex")
+});
+
+/// "3 March 2024", "3rd day of March, 2024".
+static DAY_MONTH_YEAR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"\b(\d{{1,2}})(?:st|nd|rd|th)?[ \t]+(?:day[ \t]+of[ \t]+)?({MONTHS})\.?,?[ \t]+(\d{{4}})\b"
+    ))
+    .expect("valid day-month-year regex")
+});
+
+/// "2024-03-03".
+static ISO_DATE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(\d{4})-(\d{2})-(\d{2})\b").expect("valid ISO date regex"));
+
+/// A dated fact found in a document, before it is stored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedFact {
+    pub date: NaiveDate,
+    pub description: String,
+    /// Verbatim source text supporting the fact.
+    pub excerpt: Option<String>,
+    /// Where in the source the fact appears, e.g. `line 12`.
+    pub locator: Option<String>,
+    pub confidence: f32,
+    pub extractor: &'static str,
+}
+
+fn month_number(name: &str) -> Option<u32> {
+    let lower = name.to_ascii_lowercase();
+    let month = match lower.get(..3)? {
+        "jan" => 1,
+        "feb" => 2,
+        "mar" => 3,
+        "apr" => 4,
+        "may" => 5,
+        "jun" => 6,
+        "jul" => 7,
+        "aug" => 8,
+        "sep" => 9,
+        "oct" => 10,
+        "nov" => 11,
+        "dec" => 12,
+        _ => return None,
+    };
+    Some(month)
+}
+
+fn line_locator(content: &str, offset: usize) -> String {
+    format!("line {}", content[..offset].matches('\n').count() + 1)
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut out: String = text.chars().take(max.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
+
+/// Words whose trailing period does not end a sentence.
+const ABBREVIATIONS: &[&str] = &[
+    "inc", "ltd", "corp", "co", "llc", "mr", "mrs", "ms", "dr", "prof", "hon", "no", "st", "jr",
+    "sr", "vs", "v", "e.g", "i.e", "u.s", "jan", "feb", "mar", "apr", "jun", "jul", "aug", "sep",
+    "sept", "oct", "nov", "dec",
+];
+
+/// Whether the punctuation at `idx` in `text` ends a sentence.
+fn ends_sentence(text: &str, idx: usize) -> bool {
+    if !text[idx + 1..]
+        .chars()
+        .next()
+        .is_none_or(char::is_whitespace)
+    {
+        return false;
+    }
+    if !text[idx..].starts_with('.') {
+        return true;
+    }
+    let word = text[..idx]
+        .rsplit(char::is_whitespace)
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches(['(', '"', '\'']);
+    let is_initial = word.len() == 1 && word.chars().all(|c| c.is_ascii_uppercase());
+    !is_initial && !ABBREVIATIONS.contains(&word.to_ascii_lowercase().as_str())
+}
+
+/// Sentence containing the byte range `start..end`, bounded by line breaks
+/// and sentence-ending punctuation outside the range.
+fn sentence_around(content: &str, start: usize, end: usize) -> &str {
+    let line_start = content[..start].rfind('\n').map_or(0, |idx| idx + 1);
+    let line_end = content[end..]
+        .find('\n')
+        .map_or(content.len(), |idx| end + idx);
+    let line = &content[line_start..line_end];
+    let (start, end) = (start - line_start, end - line_start);
+    let sentence_start = line[..start]
+        .rmatch_indices(['.', '!', '?', ';'])
+        .find(|(idx, _)| ends_sentence(line, *idx))
+        .map_or(0, |(idx, _)| idx + 1);
+    let sentence_end = line[end..]
+        .match_indices(['.', '!', '?', ';'])
+        .find(|(idx, _)| ends_sentence(line, end + idx))
+        .map_or(line.len(), |(idx, _)| end + idx + 1);
+    line[sentence_start..sentence_end].trim()
+}
+
+/// Drop markdown list, heading, quote, and table decoration from a line.
+fn strip_markdown(text: &str) -> String {
+    let trimmed = text
+        .trim()
+        .trim_start_matches(['#', '>', '-', '*', '|', ' ', '\t'])
+        .trim_end_matches(['|', ' ', '\t']);
+    trimmed
+        .split('|')
+        .map(str::trim)
+        .filter(|cell| !cell.is_empty())
+        .collect::<Vec<_>>()
+        .join(" — ")
+}
+
+/// A sentence is only a fact if something happens in it besides the date.
+fn describes_event(sentence: &str, date_text: &str) -> bool {
+    sentence
+        .replace(date_text, " ")
+        .split_whitespace()
+        .filter(|word| word.chars().filter(|c| c.is_alphabetic()).count() >= 2)
+        .count()
+        >= 2
+}
+
+/// Merge facts with the same date and description, keeping the most
+/// confident sighting in document order.
+fn dedupe_facts(facts: Vec<ExtractedFact>) -> Vec<ExtractedFact> {
+    let mut out: Vec<ExtractedFact> = Vec::new();
+    for fact in facts {
+        let key = fact.description.to_lowercase();
+        match out.iter_mut().find(|existing| {
+            existing.date == fact.date && existing.description.to_lowercase() == key
+        }) {
+            Some(existing) => {
+                if fact.confidence > existing.confidence {
+                    *existing = fact;
+                }
+            }
+            None => out.push(fact),
+        }
+    }
+    out.truncate(MAX_FACTS_PER_DOCUMENT);
+    out
+}
+
+/// A date written in a document: byte range, parsed date, and confidence.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DateMention {
+    pub start: usize,
+    pub end: usize,
+    pub date: NaiveDate,
+    pub confidence: f32,
+}
+
+/// Every written-out or ISO date in `content`, in document order.
+pub(crate) fn find_dates(content: &str) -> Vec<DateMention> {
+    let mut found: Vec<DateMention> = Vec::new();
+
+    for captures in MONTH_DAY_YEAR_RE.captures_iter(content) {
+        let (Some(m), Some(month), Some(day), Some(year)) = (
+            captures.get(0),
+            captures.get(1),
+            captures.get(2),
+            captures.get(3),
+        ) else {
+            continue;
+        };
+        if let (Some(month), Ok(day), Ok(year)) = (
+            month_number(month.as_str()),
+            day.as_str().parse(),
+            year.as_str().parse(),
+        ) && let Some(date) = NaiveDate::from_ymd_opt(year, month, day)
+        {
+            found.push(DateMention {
+                start: m.start(),
+                end: m.end(),
+                date,
+                confidence: 0.7,
+            });
+        }
+    }
+    for captures in DAY_MONTH_YEAR_RE.captures_iter(content) {
+        let (Some(m), Some(day), Some(month), Some(year)) = (
+            captures.get(0),
+            captures.get(1),
+            captures.get(2),
+            captures.get(3),
+        ) else {
+            continue;
+        };
+        if let (Some(month), Ok(day), Ok(year)) = (
+            month_number(month.as_str()),
+            day.as_str().parse(),
+            year.as_str().parse(),
+        ) && let Some(date) = NaiveDate::from_ymd_opt(year, month, day)
+        {
+            found.push(DateMention {
+                start: m.start(),
+                end: m.end(),
+                date,
+                confidence: 0.7,
+            });
+        }
+    }
+    for captures in ISO_DATE_RE.captures_iter(content) {
+        let (Some(m), Some(year), Some(month), Some(day)) = (
+            captures.get(0),
+            captures.get(1),
+            captures.get(2),
+            captures.get(3),
+        ) else {
+            continue;
+        };
+        if let (Ok(year), Ok(month), Ok(day)) = (
+            year.as_str().parse(),
+            month.as_str().parse(),
+            day.as_str().parse(),
+        ) && let Some(date) = NaiveDate::from_ymd_opt(year, month, day)
+        {
+            // ISO dates are often metadata (file names, headers), not events.
+            found.push(DateMention {
+                start: m.start(),
+                end: m.end(),
+                date,
+                confidence: 0.5,
+            });
+        }
+    }
+    found.sort_by_key(|mention| mention.start);
+    found
+}
+
+/// Deterministic extraction: every date that sits in a sentence describing
+/// something, with that sentence as the fact.
+pub fn extract_facts_heuristic(content: &str) -> Vec<ExtractedFact> {
+    let mut out = Vec::new();
+    for DateMention {
+        start,
+        end,
+        date,
+        confidence,
+    } in find_dates(content)
+    {
+        let sentence = sentence_around(content, start, end);
+        if !describes_event(sentence, &content[start..end]) {
+            continue;
+        }
+        let description = strip_markdown(sentence);
+        if description.is_empty() {
+            continue;
+        }
+        out.push(ExtractedFact {
+            date,
+            description: truncate_chars(&description, DESCRIPTION_CHARS),
+            excerpt: Some(truncate_chars(sentence, EXCERPT_CHARS)),
+            locator: Some(line_locator(content, start)),
+            confidence,
+            extractor: "heuristic",
+        });
+    }
+    dedupe_facts(out)
+}
+
+#[derive(Debug, Deserialize)]
+struct LlmFact {
+    date: String,
+    event: String,
+    #[serde(default)]
+    quote: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LlmFacts {
+    #[serde(default)]
+    facts: Vec<LlmFact>,
+}
+
+fn parse_llm_facts(raw: &str, content: &str) -> Option<Vec<ExtractedFact>> {
+    let start = raw.find('{')?;
+    let end = raw.rfind('}')?;
+    let parsed: LlmFacts = serde_json::from_str(raw.get(start..=end)?).ok()?;
+    Some(
+        parsed
+            .facts
+            .into_iter()
+            .filter_map(|fact| {
+                let date = NaiveDate::parse_from_str(fact.date.trim(), "%Y-%m-%d").ok()?;
+                let description = fact.event.trim();
+                if description.is_empty() {
+                    return None;
+                }
+                // A quote that appears verbatim in the document grounds the
+                // fact; an unverifiable one is kept at lower confidence.
+                let quote = fact
+                    .quote
+                    .map(|q| q.trim().to_string())
+                    .filter(|q| !q.is_empty());
+                let offset = quote.as_deref().and_then(|q| content.find(q));
+                Some(ExtractedFact {
+                    date,
+                    description: truncate_chars(description, DESCRIPTION_CHARS),
+                    excerpt: quote.map(|q| truncate_chars(&q, EXCERPT_CHARS)),
+                    locator: offset.map(|idx| line_locator(content, idx)),
+                    confidence: if offset.is_some() { 0.85 } else { 0.5 },
+                    extractor: "llm",
+                })
+            })
+            .collect(),
+    )
+}
+
+async fn extract_with_llm(
+    llm: &dyn LlmProvider,
+    path: &str,
+    content: &str,
+) -> Option<Vec<ExtractedFact>> {
+    let excerpt: String = content.chars().take(LLM_EXCERPT_CHARS).collect();
+    let prompt = format!(
+        r#"List the dated events in this legal document that belong in a case
+chronology: agreements signed, notices sent, payments, breaches, filings,
+hearings, and similar. Skip dates that are only document metadata.
+
+File name: {path}
+
+Content excerpt:
+---
+{excerpt}
+---
+
+Respond with JSON only. "quote" must be copied verbatim from the document:
+{{"facts": [{{"date": "YYYY-MM-DD", "event": "<one-sentence description>", "quote": "<supporting text>"}}]}}"#
+    );
+    let request = CompletionRequest::new(vec![ChatMessage::user(prompt)])
+        .with_max_tokens(1_500)
+        .with_temperature(0.0)
+        .with_task(LlmTask::Extraction);
+    let response = match llm.complete(request).await {
+        Ok(response) => response,
+        Err(err) => {
+            tracing::debug!("LLM fact extraction failed for '{path}': {err}");
+            return None;
+        }
+    };
+    let facts = parse_llm_facts(&response.content, content);
+    if facts.is_none() {
+        tracing::debug!("LLM fact extraction for '{path}' returned unparseable output");
+    }
+    facts
+}
+
+/// Extract dated facts from a document.
+///
+/// When an LLM is available its facts are preferred, and heuristic facts
+/// are only kept for dates the LLM did not cover.
+pub async fn extract_facts(
+    llm: Option<&dyn LlmProvider>,
+    path: &str,
+    content: &str,
+) -> Vec<ExtractedFact> {
+    let heuristic = extract_facts_heuristic(content);
+    let Some(mut facts) = (match llm {
+        Some(llm) => extract_with_llm(llm, path, content).await,
+        None => None,
+    }) else {
+        return heuristic;
+    };
+    let covered: HashSet<NaiveDate> = facts.iter().map(|fact| fact.date).collect();
+    facts.extend(
+        heuristic
+            .into_iter()
+            .filter(|fact| !covered.contains(&fact.date)),
+    );
+    facts.sort_by_key(|fact| fact.date);
+    dedupe_facts(facts)
+}
+
+/// Workspace path of the generated key facts log.
+pub fn key_facts_path(matter_root: &str, matter_id: &str) -> String {
+    format!(
+        "{}/{}/facts/key_facts.md",
+        matter_root.trim_end_matches('/'),
+        matter_id
+    )
+}
+
+/// Workspace path of the generated chronology.
+pub fn chronology_path(matter_root: &str, matter_id: &str) -> String {
+    format!(
+        "{}/{}/templates/chronology.md",
+        matter_root.trim_end_matches('/'),
+        matter_id
+    )
+}
+
+const GENERATED_NOTE: &str = "_Generated from the matter fact store by `extract_matter_facts`; \
+     re-run extraction instead of editing this table._";
+
+fn source_cell(fact: &MatterFactRecord) -> String {
+    match fact.source_locator.as_deref() {
+        Some(locator) => table_cell(&format!("{} ({locator})", fact.source_path)),
+        None => table_cell(&fact.source_path),
+    }
+}
+
+fn confidence_label(confidence: f64) -> &'static str {
+    if confidence >= 0.8 {
+        "High"
+    } else if confidence >= 0.5 {
+        "Medium"
+    } else {
+        "Low"
+    }
+}
+
+/// Render `facts/key_facts.md` from stored facts (expected in date order).
+pub fn render_key_facts(facts: &[MatterFactRecord]) -> String {
+    let mut out = format!(
+        "# Key Facts Log\n\n{GENERATED_NOTE}\n\n| Fact | Source | Confidence | Notes |\n|---|---|---|---|\n"
+    );
+    for fact in facts {
+        let notes = match fact.source_excerpt.as_deref() {
+            Some(excerpt) => format!("{} extraction: \"{excerpt}\"", fact.extractor),
+            None => format!("{} extraction", fact.extractor),
+        };
+        out.push_str(&format!(
+            "| {}: {} | {} | {} | {} |\n",
+            fact.fact_date.format("%Y-%m-%d"),
+            table_cell(&fact.description),
+            source_cell(fact),
+            confidence_label(fact.confidence),
+            table_cell(&notes),
+        ));
+    }
+    out
+}
+
+/// Render `templates/chronology.md` from stored facts (expected in date order).
+pub fn render_chronology(facts: &[MatterFactRecord]) -> String {
+    let mut out =
+        format!("# Chronology\n\n{GENERATED_NOTE}\n\n| Date | Event | Source |\n|---|---|---|\n");
+    for fact in facts {
+        out.push_str(&format!(
+            "| {} | {} | {} |\n",
+            fact.fact_date.format("%Y-%m-%d"),
+            table_cell(&fact.description),
+            source_cell(fact),
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::StubLlm;
+
+    const LETTER: &str = "# Demand Letter\n\n\
+        Dated: 2024-06-01\n\n\
+        On March 3, 2023, Acme Inc. and Globex LLC signed the Master Services Agreement. \
+        Globex failed to deliver the first milestone due on 15 September 2023.\n\
+        - Acme sent a notice of breach on Oct. 2nd 2023.\n";
+
+    fn descriptions(facts: &[ExtractedFact]) -> Vec<&str> {
+        facts.iter().map(|f| f.description.as_str()).collect()
+    }
+
+    #[test]
+    fn heuristic_finds_dated_sentences() {
+        let facts = extract_facts_heuristic(LETTER);
+        let dates: Vec<String> = facts.iter().map(|f| f.date.to_string()).collect();
+        assert_eq!(dates, vec!["2023-03-03", "2023-09-15", "2023-10-02"]);
+        assert!(
+            facts[0]
+                .description
+                .starts_with("On March 3, 2023, Acme Inc.")
+        );
+        assert!(facts[0].description.ends_with("Master Services Agreement."));
+        assert!(facts[1].description.starts_with("Globex failed"));
+        assert_eq!(
+            facts[2].description,
+            "Acme sent a notice of breach on Oct. 2nd 2023."
+        );
+        assert_eq!(facts[2].locator.as_deref(), Some("line 6"));
+        assert!(facts.iter().all(|f| f.extractor == "heuristic"));
+    }
+
+    #[test]
+    fn heuristic_skips_bare_and_invalid_dates() {
+        assert!(extract_facts_heuristic("Date: March 3, 2023").is_empty());
+        assert!(
+            extract_facts_heuristic("Payment was made on February 30, 2023 in full.").is_empty()
+        );
+    }
+
+    #[test]
+    fn parse_llm_facts_grounds_quotes() {
+        let parsed = parse_llm_facts(
+            r#"{"facts": [
+                {"date": "2023-03-03", "event": "MSA signed", "quote": "signed the Master Services Agreement"},
+                {"date": "2023-04-01", "event": "Kickoff call", "quote": "not in the letter"},
+                {"date": "sometime", "event": "Ignored"}
+            ]}"#,
+            LETTER,
+        )
+        .expect("parsed");
+        assert_eq!(descriptions(&parsed), vec!["MSA signed", "Kickoff call"]);
+        assert_eq!(parsed[0].confidence, 0.85);
+        assert_eq!(parsed[0].locator.as_deref(), Some("line 5"));
+        assert_eq!(parsed[1].confidence, 0.5);
+        assert!(parsed[1].locator.is_none());
+        assert!(parse_llm_facts("no json", LETTER).is_none());
+    }
+
+    #[tokio::test]
+    async fn extract_facts_prefers_llm_and_fills_gaps() {
+        let llm = StubLlm::new(
+            r#"{"facts": [{"date": "2023-03-03", "event": "MSA signed", "quote": "signed the Master Services Agreement"}]}"#,
+        );
+        let facts = extract_facts(Some(&llm), "letter.md", LETTER).await;
+        assert_eq!(facts.len(), 3);
+        assert_eq!(facts[0].description, "MSA signed");
+        assert_eq!(facts[0].extractor, "llm");
+        assert!(facts[1..].iter().all(|f| f.extractor == "heuristic"));
+
+        let failing = StubLlm::failing("stub");
+        let facts = extract_facts(Some(&failing), "letter.md", LETTER).await;
+        assert_eq!(facts.len(), 3);
+    }
+
+    #[test]
+    fn renders_tables_with_citations() {
+        let now = chrono::Utc::now();
+        let fact = MatterFactRecord {
+            id: uuid::Uuid::new_v4(),
+            user_id: "default".to_string(),
+            matter_id: "acme".to_string(),
+            fact_date: NaiveDate::from_ymd_opt(2023, 3, 3).unwrap(),
+            description: "MSA signed | executed".to_string(),
+            source_path: "matters/acme/contracts/msa.md".to_string(),
+            source_locator: Some("line 5".to_string()),
+            source_excerpt: Some("signed the MSA".to_string()),
+            confidence: 0.85,
+            extractor: "llm".to_string(),
+            created_by: "default".to_string(),
+            created_at: now,
+            updated_at: now,
+        };
+        let key_facts = render_key_facts(std::slice::from_ref(&fact));
+        assert!(key_facts.starts_with("# Key Facts Log\n"));
+        assert!(key_facts.contains("| Fact | Source | Confidence | Notes |"));
+        assert!(key_facts.contains(
+            "| 2023-03-03: MSA signed \\| executed | matters/acme/contracts/msa.md (line 5) | High | llm extraction: \"signed the MSA\" |"
+        ));
+
+        let chronology = render_chronology(&[fact]);
+        assert!(chronology.contains("| Date | Event | Source |"));
+        assert!(chronology.contains(
+            "| 2023-03-03 | MSA signed \\| executed | matters/acme/contracts/msa.md (line 5) |"
+        ));
+        assert_eq!(
+            key_facts_path("matters/", "acme"),
+            "matters/acme/facts/key_facts.md"
+        );
+        assert_eq!(
+            chronology_path("matters", "acme"),
+            "matters/acme/templates/chronology.md"
+        );
+    }
+}
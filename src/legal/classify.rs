@@ -0,0 +1,381 @@
+//! Matter document classification for ingestion.
+//!
+//! Assigns a [`MatterDocumentCategory`] and a destination folder to incoming
+//! documents. An LLM classifier is used when a provider is available; the
+//! deterministic heuristic (folder hints, then filename/content keywords)
+//! is the fallback and is also used for bulk backfills.
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::MatterDocumentCategory;
+use crate::llm::{CacheControl, ChatMessage, CompletionRequest, LlmProvider, LlmTask};
+
+/// Characters of document content sent to the LLM classifier.
+const LLM_EXCERPT_CHARS: usize = 4_000;
+
+/// Minimum keyword score before content heuristics override `internal`.
+const MIN_KEYWORD_SCORE: usize = 2;
+
+/// Classifications of unchanged documents are reused for a week.
+const CLASSIFICATION_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(7 * 24 * 3600);
+
+/// How a classification was produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClassificationSource {
+    /// The document already lives in a category folder.
+    Path,
+    /// Filename/content keyword scoring.
+    Keywords,
+    Llm,
+    /// Nothing matched; defaulted to `internal`.
+    Default,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DocumentClassification {
+    pub category: MatterDocumentCategory,
+    /// Folder (relative to the matter root) the document should live in.
+    pub suggested_folder: &'static str,
+    pub confidence: f32,
+    pub source: ClassificationSource,
+}
+
+impl DocumentClassification {
+    fn new(
+        category: MatterDocumentCategory,
+        confidence: f32,
+        source: ClassificationSource,
+    ) -> Self {
+        Self {
+            category,
+            suggested_folder: suggested_folder(category),
+            confidence,
+            source,
+        }
+    }
+}
+
+/// Matter-relative folder that holds documents of `category`.
+pub fn suggested_folder(category: MatterDocumentCategory) -> &'static str {
+    match category {
+        MatterDocumentCategory::Pleading => "pleadings",
+        MatterDocumentCategory::Correspondence => "communications",
+        MatterDocumentCategory::Contract => "contracts",
+        MatterDocumentCategory::Filing => "filings",
+        MatterDocumentCategory::Evidence => "evidence",
+        MatterDocumentCategory::Internal => "notes",
+    }
+}
+
+/// Category implied by the folder a document is stored in, if any.
+pub fn category_from_path(path: &str) -> Option<MatterDocumentCategory> {
+    let lower = path.to_ascii_lowercase();
+    if lower.contains("/pleading") {
+        Some(MatterDocumentCategory::Pleading)
+    } else if lower.contains("/filing") {
+        Some(MatterDocumentCategory::Filing)
+    } else if lower.contains("/evidence")
+        || lower.contains("/exhibit")
+        || lower.contains("/transcript")
+        || lower.contains("/medical/records")
+    {
+        Some(MatterDocumentCategory::Evidence)
+    } else if lower.contains("/contract") || lower.contains("/agreement") {
+        Some(MatterDocumentCategory::Contract)
+    } else if lower.contains("/correspondence") || lower.contains("/communication") {
+        Some(MatterDocumentCategory::Correspondence)
+    } else {
+        None
+    }
+}
+
+const KEYWORDS: &[(MatterDocumentCategory, &[&str])] = &[
+    (
+        MatterDocumentCategory::Pleading,
+        &[
+            "complaint",
+            "plaintiff",
+            "defendant",
+            "counterclaim",
+            "cause of action",
+            "prayer for relief",
+            "wherefore",
+            "comes now",
+            "statement of claim",
+            "statement of defence",
+        ],
+    ),
+    (
+        MatterDocumentCategory::Filing,
+        &[
+            "certificate of service",
+            "proof of service",
+            "notice of filing",
+            "clerk of the court",
+            "docket no",
+            "notice of motion",
+            "proposed order",
+            "filed electronically",
+        ],
+    ),
+    (
+        MatterDocumentCategory::Contract,
+        &[
+            "agreement",
+            "whereas",
+            "hereinafter",
+            "the parties agree",
+            "governing law",
+            "indemnif",
+            "termination",
+            "in witness whereof",
+            "effective date",
+        ],
+    ),
+    (
+        MatterDocumentCategory::Correspondence,
+        &[
+            "dear ",
+            "sincerely",
+            "kind regards",
+            "best regards",
+            "subject:",
+            "from:",
+            "cc:",
+            "without prejudice",
+        ],
+    ),
+    (
+        MatterDocumentCategory::Evidence,
+        &[
+            "exhibit",
+            "deposition",
+            "transcript",
+            "bates",
+            "affidavit",
+            "sworn",
+            "declaration of",
+            "chain of custody",
+        ],
+    ),
+];
+
+fn keyword_scores(path: &str, content: &str) -> Vec<(MatterDocumentCategory, usize)> {
+    let file_name = path
+        .rsplit('/')
+        .next()
+        .unwrap_or(path)
+        .to_ascii_lowercase()
+        .replace(['_', '-', '.'], " ");
+    let body = content.to_lowercase();
+    KEYWORDS
+        .iter()
+        .map(|(category, words)| {
+            let score = words
+                .iter()
+                .map(|word| {
+                    let in_name = usize::from(file_name.contains(word.trim())) * 2;
+                    in_name + usize::from(body.contains(word))
+                })
+                .sum();
+            (*category, score)
+        })
+        .collect()
+}
+
+/// Deterministic classification from folder hints and keywords.
+pub fn classify_heuristic(path: &str, content: &str) -> DocumentClassification {
+    if let Some(category) = category_from_path(path) {
+        return DocumentClassification::new(category, 0.9, ClassificationSource::Path);
+    }
+
+    let scores = keyword_scores(path, content);
+    let total: usize = scores.iter().map(|(_, score)| score).sum();
+    // Ties resolve to the earliest category in KEYWORDS order.
+    let best = scores
+        .iter()
+        .copied()
+        .reduce(|best, next| if next.1 > best.1 { next } else { best });
+    match best {
+        Some((category, score)) if score >= MIN_KEYWORD_SCORE => {
+            let confidence = (score as f32 / total.max(1) as f32).clamp(0.3, 0.85);
+            DocumentClassification::new(category, confidence, ClassificationSource::Keywords)
+        }
+        _ => DocumentClassification::new(
+            MatterDocumentCategory::Internal,
+            0.2,
+            ClassificationSource::Default,
+        ),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LlmClassification {
+    category: String,
+    #[serde(default)]
+    confidence: Option<f32>,
+}
+
+fn parse_llm_classification(raw: &str) -> Option<(MatterDocumentCategory, f32)> {
+    let start = raw.find('{')?;
+    let end = raw.rfind('}')?;
+    let parsed: LlmClassification = serde_json::from_str(raw.get(start..=end)?).ok()?;
+    let category = MatterDocumentCategory::from_db_value(
+        parsed.category.trim().to_ascii_lowercase().as_str(),
+    )?;
+    Some((category, parsed.confidence.unwrap_or(0.7).clamp(0.0, 1.0)))
+}
+
+async fn classify_with_llm(
+    llm: &dyn LlmProvider,
+    path: &str,
+    content: &str,
+) -> Option<DocumentClassification> {
+    let excerpt: String = content.chars().take(LLM_EXCERPT_CHARS).collect();
+    let prompt = format!(
+        r#"Classify this legal matter document into exactly one category:
+pleading, correspondence, contract, filing, evidence, internal.
+
+- pleading: complaints, answers, statements of claim/defence, counterclaims
+- filing: motions, notices, orders, certificates of service, court submissions
+- contract: agreements, amendments, NDAs, term sheets
+- correspondence: letters, emails, memos to or from other parties
+- evidence: exhibits, transcripts, affidavits, productions
+- internal: firm notes, research, drafts not in another category
+
+File name: {path}
+
+Content excerpt:
+---
+{excerpt}
+---
+
+Respond with JSON only: {{"category": "<category>", "confidence": 0.0-1.0}}"#
+    );
+    let request = CompletionRequest::new(vec![ChatMessage::user(prompt)])
+        .with_max_tokens(100)
+        .with_temperature(0.0)
+        .with_task(LlmTask::Extraction)
+        .with_cache_control(CacheControl::Cache {
+            ttl: Some(CLASSIFICATION_CACHE_TTL),
+        });
+    let response = match llm.complete(request).await {
+        Ok(response) => response,
+        Err(err) => {
+            tracing::debug!("LLM document classification failed for '{path}': {err}");
+            return None;
+        }
+    };
+    let Some((category, confidence)) = parse_llm_classification(&response.content) else {
+        tracing::debug!("LLM document classification for '{path}' returned unparseable output");
+        return None;
+    };
+    Some(DocumentClassification::new(
+        category,
+        confidence,
+        ClassificationSource::Llm,
+    ))
+}
+
+/// Classify a document at ingestion.
+///
+/// A folder hint always wins (the user filed it deliberately). Otherwise the
+/// LLM is consulted when available, falling back to keyword heuristics if
+/// the call fails or returns something unusable.
+pub async fn classify_document(
+    llm: Option<&dyn LlmProvider>,
+    path: &str,
+    content: &str,
+) -> DocumentClassification {
+    let heuristic = classify_heuristic(path, content);
+    if heuristic.source == ClassificationSource::Path {
+        return heuristic;
+    }
+    if let Some(llm) = llm
+        && let Some(classification) = classify_with_llm(llm, path, content).await
+    {
+        return classification;
+    }
+    heuristic
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::StubLlm;
+
+    #[test]
+    fn folder_hints_take_priority() {
+        let result = classify_heuristic("matters/demo/pleadings/complaint.md", "Dear counsel");
+        assert_eq!(result.category, MatterDocumentCategory::Pleading);
+        assert_eq!(result.source, ClassificationSource::Path);
+
+        let result = classify_heuristic("matters/demo/communications/log.md", "");
+        assert_eq!(result.category, MatterDocumentCategory::Correspondence);
+        assert_eq!(result.suggested_folder, "communications");
+    }
+
+    #[test]
+    fn keywords_classify_unsorted_uploads() {
+        let contract = classify_heuristic(
+            "uploads/nda.md",
+            "This Agreement is made WHEREAS the parties agree. Governing law: Ontario.",
+        );
+        assert_eq!(contract.category, MatterDocumentCategory::Contract);
+        assert_eq!(contract.source, ClassificationSource::Keywords);
+        assert_eq!(contract.suggested_folder, "contracts");
+
+        let letter = classify_heuristic(
+            "uploads/letter.txt",
+            "Dear Ms. Smith,\n\nPlease find enclosed...\n\nSincerely,\nCounsel",
+        );
+        assert_eq!(letter.category, MatterDocumentCategory::Correspondence);
+
+        let exhibit = classify_heuristic("uploads/exhibit_a_deposition_transcript.txt", "Q: A:");
+        assert_eq!(exhibit.category, MatterDocumentCategory::Evidence);
+    }
+
+    #[test]
+    fn weak_signals_default_to_internal() {
+        let result = classify_heuristic("uploads/notes.md", "Call client tomorrow.");
+        assert_eq!(result.category, MatterDocumentCategory::Internal);
+        assert_eq!(result.source, ClassificationSource::Default);
+        assert_eq!(result.suggested_folder, "notes");
+    }
+
+    #[test]
+    fn parse_llm_classification_accepts_wrapped_json() {
+        assert_eq!(
+            parse_llm_classification("Sure: {\"category\": \"Filing\", \"confidence\": 0.8}"),
+            Some((MatterDocumentCategory::Filing, 0.8))
+        );
+        assert_eq!(parse_llm_classification("{\"category\": \"memo\"}"), None);
+        assert_eq!(parse_llm_classification("pleading"), None);
+    }
+
+    #[tokio::test]
+    async fn classify_document_prefers_llm_and_falls_back() {
+        let llm = StubLlm::new(r#"{"category": "evidence", "confidence": 0.95}"#);
+        let result = classify_document(Some(&llm), "uploads/scan.txt", "Dear counsel").await;
+        assert_eq!(result.category, MatterDocumentCategory::Evidence);
+        assert_eq!(result.source, ClassificationSource::Llm);
+        assert_eq!(llm.calls(), 1);
+
+        // Folder hints short-circuit the LLM call.
+        let result =
+            classify_document(Some(&llm), "matters/demo/contracts/msa.md", "anything").await;
+        assert_eq!(result.category, MatterDocumentCategory::Contract);
+        assert_eq!(llm.calls(), 1);
+
+        let failing = StubLlm::failing("stub");
+        let result = classify_document(
+            Some(&failing),
+            "uploads/letter.txt",
+            "Dear counsel, sincerely yours",
+        )
+        .await;
+        assert_eq!(result.category, MatterDocumentCategory::Correspondence);
+        assert_eq!(result.source, ClassificationSource::Keywords);
+    }
+}
@@ -0,0 +1,424 @@
+//! Client communication preferences and consent.
+//!
+//! Each client can have preferences (channels they may be contacted on,
+//! quiet hours in their timezone, the language to write in, and disclaimers
+//! every message must carry) and a history of consent decisions per channel.
+//! Every client-facing send — approved status report emails, SMS deadline
+//! reminders, and SMS replies — is cleared through [`clear_client_send`]
+//! first, so the rules live in one place:
+//!
+//! - The latest consent decision for the channel must not be a revocation.
+//! - Once preferences are saved, the channel must be allowed and the client
+//!   must have granted consent for it.
+//! - Messages the firm starts are held during quiet hours. Replies to a
+//!   message from the client are not.
+//!
+//! Clients without preferences keep the old behaviour: anything not revoked
+//! may be sent, with no disclaimers.
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use uuid::Uuid;
+
+use crate::agent::execution_window::ExecutionWindow;
+use crate::db::{
+    ClientCommunicationConsentRecord, ClientCommunicationPreferencesRecord, CommunicationChannel,
+    CommunicationConsentStatus, Database,
+};
+use crate::error::DatabaseError;
+
+pub const MAX_DISCLAIMERS: usize = 5;
+pub const MAX_DISCLAIMER_CHARS: usize = 1_000;
+pub const MAX_LANGUAGE_CHARS: usize = 35;
+
+/// A client's preferences and consent history.
+#[derive(Debug, Clone, Default)]
+pub struct ClientCommsPolicy {
+    pub preferences: Option<ClientCommunicationPreferencesRecord>,
+    /// Consent decisions, newest first.
+    pub consents: Vec<ClientCommunicationConsentRecord>,
+}
+
+/// What a cleared send must honour.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SendClearance {
+    pub disclaimers: Vec<String>,
+    pub language: Option<String>,
+}
+
+impl SendClearance {
+    /// `body` with the required disclaimers appended.
+    pub fn apply(&self, body: &str) -> String {
+        let mut out = body.trim_end().to_string();
+        for disclaimer in &self.disclaimers {
+            out.push_str("\n\n");
+            out.push_str(disclaimer);
+        }
+        out
+    }
+
+    /// Instruction for a model drafting the message on `today`, when the
+    /// client reads a language other than US English (see
+    /// [`crate::legal::locale::drafting_instruction`]).
+    pub fn drafting_instruction(&self, today: NaiveDate) -> Option<String> {
+        self.language
+            .as_deref()
+            .and_then(|language| crate::legal::locale::drafting_instruction(language, today))
+    }
+
+    /// Locale for dates, numbers, and template variants.
+    pub fn locale(&self) -> crate::legal::locale::Locale {
+        crate::legal::locale::client_locale(self.language.as_deref())
+    }
+}
+
+/// Why a send was refused. `retry_at` is set when the refusal is only
+/// quiet hours.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SendBlock {
+    pub reason: String,
+    pub retry_at: Option<DateTime<Utc>>,
+}
+
+impl SendBlock {
+    fn refused(reason: String) -> Self {
+        Self {
+            reason,
+            retry_at: None,
+        }
+    }
+}
+
+impl ClientCommsPolicy {
+    /// The consent decision in force for `channel`, if any.
+    pub fn current_consent(
+        &self,
+        channel: CommunicationChannel,
+    ) -> Option<&ClientCommunicationConsentRecord> {
+        self.consents
+            .iter()
+            .find(|consent| consent.channel == channel)
+    }
+
+    fn timezone(&self) -> Tz {
+        self.preferences
+            .as_ref()
+            .and_then(|prefs| prefs.timezone.as_deref())
+            .and_then(|tz| tz.parse::<Tz>().ok())
+            .unwrap_or(Tz::UTC)
+    }
+
+    /// Earliest instant at or after `at` outside the client's quiet hours.
+    pub fn next_send_time(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let windows = match self.preferences.as_ref() {
+            Some(prefs) if !prefs.quiet_hours.is_empty() => &prefs.quiet_hours,
+            _ => return at,
+        };
+        let tz = self.timezone();
+        let start = at.with_timezone(&tz).naive_local();
+        let mut local = start;
+        // Adjacent or overlapping windows are walked one after another; the
+        // bound keeps a window list covering the whole week from looping.
+        for _ in 0..16 {
+            let Some(end) = windows
+                .iter()
+                .filter(|window| window.contains(local))
+                .map(|window| quiet_window_end(window, local))
+                .max()
+            else {
+                break;
+            };
+            local = end;
+        }
+        if local == start {
+            return at;
+        }
+        tz.from_local_datetime(&local)
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|| at + (local - start))
+    }
+
+    /// Whether a message on `channel` may be sent at `at`.
+    /// `client_initiated` marks a reply to a message from the client, which
+    /// quiet hours do not hold back.
+    pub fn check(
+        &self,
+        channel: CommunicationChannel,
+        at: DateTime<Utc>,
+        client_initiated: bool,
+    ) -> Result<SendClearance, SendBlock> {
+        let consent = self.current_consent(channel);
+        if let Some(consent) = consent
+            && consent.status == CommunicationConsentStatus::Revoked
+        {
+            return Err(SendBlock::refused(format!(
+                "client revoked {} consent on {} ({})",
+                channel.as_str(),
+                consent.consented_at.date_naive(),
+                consent.source
+            )));
+        }
+        let Some(prefs) = self.preferences.as_ref() else {
+            return Ok(SendClearance::default());
+        };
+        if !prefs.allowed_channels.contains(&channel) {
+            return Err(SendBlock::refused(format!(
+                "client does not accept {} messages",
+                channel.as_str()
+            )));
+        }
+        if consent.is_none() {
+            return Err(SendBlock::refused(format!(
+                "no {} consent on record for client",
+                channel.as_str()
+            )));
+        }
+        if !client_initiated {
+            let next = self.next_send_time(at);
+            if next > at {
+                return Err(SendBlock {
+                    reason: format!(
+                        "client quiet hours until {}",
+                        next.with_timezone(&self.timezone())
+                            .format("%Y-%m-%d %H:%M %Z")
+                    ),
+                    retry_at: Some(next),
+                });
+            }
+        }
+        Ok(SendClearance {
+            disclaimers: prefs.disclaimers.clone(),
+            language: prefs.language.clone(),
+        })
+    }
+}
+
+/// Local end of the quiet `window` that contains `local`.
+fn quiet_window_end(window: &ExecutionWindow, local: NaiveDateTime) -> NaiveDateTime {
+    let date = local.date();
+    if window.start < window.end || local.time() < window.end {
+        date.and_time(window.end)
+    } else {
+        (date + Duration::days(1)).and_time(window.end)
+    }
+}
+
+/// Check preference fields before they are stored.
+pub fn validate_preferences(
+    timezone: Option<&str>,
+    language: Option<&str>,
+    disclaimers: &[String],
+) -> Result<(), String> {
+    if let Some(tz) = timezone {
+        tz.parse::<Tz>()
+            .map_err(|_| format!("unknown timezone '{tz}'"))?;
+    }
+    if let Some(language) = language
+        && language.chars().count() > MAX_LANGUAGE_CHARS
+    {
+        return Err(format!(
+            "language must be at most {MAX_LANGUAGE_CHARS} characters"
+        ));
+    }
+    if disclaimers.len() > MAX_DISCLAIMERS {
+        return Err(format!("at most {MAX_DISCLAIMERS} disclaimers are allowed"));
+    }
+    if disclaimers
+        .iter()
+        .any(|text| text.trim().is_empty() || text.chars().count() > MAX_DISCLAIMER_CHARS)
+    {
+        return Err(format!(
+            "disclaimers must be non-empty and at most {MAX_DISCLAIMER_CHARS} characters"
+        ));
+    }
+    Ok(())
+}
+
+/// Load a client's preferences and consent history.
+pub async fn load_policy(
+    store: &dyn Database,
+    user_id: &str,
+    client_id: Uuid,
+) -> Result<ClientCommsPolicy, DatabaseError> {
+    Ok(ClientCommsPolicy {
+        preferences: store
+            .get_client_communication_preferences(user_id, client_id)
+            .await?,
+        consents: store
+            .list_client_communication_consents(user_id, client_id)
+            .await?,
+    })
+}
+
+/// Locale for documents and messages to a client, from their preferred
+/// language.
+pub async fn preferred_locale(
+    store: &dyn Database,
+    user_id: &str,
+    client_id: Uuid,
+) -> Result<crate::legal::locale::Locale, DatabaseError> {
+    let preferences = store
+        .get_client_communication_preferences(user_id, client_id)
+        .await?;
+    Ok(crate::legal::locale::client_locale(
+        preferences
+            .as_ref()
+            .and_then(|prefs| prefs.language.as_deref()),
+    ))
+}
+
+/// Clear a client-facing message on `channel` for sending at `now`.
+/// A failure to read the client's records refuses the send.
+pub async fn clear_client_send(
+    store: &dyn Database,
+    user_id: &str,
+    client_id: Uuid,
+    channel: CommunicationChannel,
+    now: DateTime<Utc>,
+    client_initiated: bool,
+) -> Result<SendClearance, SendBlock> {
+    let policy = load_policy(store, user_id, client_id)
+        .await
+        .map_err(|err| {
+            SendBlock::refused(format!("failed to read communication preferences: {err}"))
+        })?;
+    policy.check(channel, now, client_initiated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn prefs(
+        channels: Vec<CommunicationChannel>,
+        quiet_hours: serde_json::Value,
+    ) -> ClientCommunicationPreferencesRecord {
+        ClientCommunicationPreferencesRecord {
+            user_id: "default".to_string(),
+            client_id: Uuid::nil(),
+            allowed_channels: channels,
+            quiet_hours: serde_json::from_value(quiet_hours).unwrap(),
+            timezone: Some("America/New_York".to_string()),
+            language: Some("es".to_string()),
+            disclaimers: vec!["Attorney-client privileged.".to_string()],
+            updated_by: "default".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn consent(
+        channel: CommunicationChannel,
+        status: CommunicationConsentStatus,
+        when: &str,
+    ) -> ClientCommunicationConsentRecord {
+        ClientCommunicationConsentRecord {
+            id: Uuid::new_v4(),
+            user_id: "default".to_string(),
+            client_id: Uuid::nil(),
+            channel,
+            status,
+            source: "engagement letter".to_string(),
+            consented_at: at(when),
+            recorded_by: "default".to_string(),
+            created_at: at(when),
+        }
+    }
+
+    #[test]
+    fn clients_without_preferences_are_only_blocked_by_revocation() {
+        let mut policy = ClientCommsPolicy::default();
+        let now = at("2026-10-14T03:00:00Z");
+        assert_eq!(
+            policy.check(CommunicationChannel::Email, now, false),
+            Ok(SendClearance::default())
+        );
+        policy.consents.push(consent(
+            CommunicationChannel::Email,
+            CommunicationConsentStatus::Revoked,
+            "2026-10-01T12:00:00Z",
+        ));
+        let block = policy
+            .check(CommunicationChannel::Email, now, false)
+            .unwrap_err();
+        assert!(block.reason.contains("revoked email consent on 2026-10-01"));
+        assert!(policy.check(CommunicationChannel::Sms, now, false).is_ok());
+    }
+
+    #[test]
+    fn preferences_require_allowed_channel_and_consent() {
+        let mut policy = ClientCommsPolicy {
+            preferences: Some(prefs(
+                vec![CommunicationChannel::Email],
+                serde_json::json!([]),
+            )),
+            consents: Vec::new(),
+        };
+        let now = at("2026-10-14T15:00:00Z");
+        assert!(
+            policy
+                .check(CommunicationChannel::Sms, now, false)
+                .unwrap_err()
+                .reason
+                .contains("does not accept sms")
+        );
+        assert!(
+            policy
+                .check(CommunicationChannel::Email, now, false)
+                .unwrap_err()
+                .reason
+                .contains("no email consent")
+        );
+        policy.consents.push(consent(
+            CommunicationChannel::Email,
+            CommunicationConsentStatus::Granted,
+            "2026-09-01T12:00:00Z",
+        ));
+        let clearance = policy
+            .check(CommunicationChannel::Email, now, false)
+            .unwrap();
+        assert_eq!(
+            clearance.apply("Your hearing is set.\n"),
+            "Your hearing is set.\n\nAttorney-client privileged."
+        );
+        assert!(
+            clearance
+                .drafting_instruction(now.date_naive())
+                .is_some_and(|text| text.starts_with("Write the message in Spanish (español)."))
+        );
+    }
+
+    #[test]
+    fn quiet_hours_hold_firm_initiated_messages_until_they_end() {
+        let policy = ClientCommsPolicy {
+            preferences: Some(prefs(
+                vec![CommunicationChannel::Sms],
+                serde_json::json!([{"start": "21:00", "end": "08:00"}]),
+            )),
+            consents: vec![consent(
+                CommunicationChannel::Sms,
+                CommunicationConsentStatus::Granted,
+                "2026-09-01T12:00:00Z",
+            )],
+        };
+        // 02:00 UTC is 22:00 the previous evening in New York (EDT).
+        let late = at("2026-10-14T02:00:00Z");
+        let block = policy
+            .check(CommunicationChannel::Sms, late, false)
+            .unwrap_err();
+        assert_eq!(block.retry_at, Some(at("2026-10-14T12:00:00Z")));
+        assert!(block.reason.contains("quiet hours until 2026-10-14 08:00"));
+        assert!(policy.check(CommunicationChannel::Sms, late, true).is_ok());
+        assert_eq!(
+            policy.next_send_time(at("2026-10-14T16:00:00Z")),
+            at("2026-10-14T16:00:00Z")
+        );
+    }
+}
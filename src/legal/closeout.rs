@@ -0,0 +1,227 @@
+//! Matter closeout: the checks that must pass before a file is closed, the
+//! closing letter, and the retention clock.
+//!
+//! A matter closes only when nothing is left unbilled, no deadline is open,
+//! and its trust balance is zero. Closing renders the matter's
+//! `closing_letter.md` template (or the built-in one), records `closed_at`,
+//! and calendars the end of the retention period as an internal deadline.
+
+use chrono::{DateTime, Months, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+/// Template name looked up among the matter's and the firm's templates.
+pub const CLOSING_LETTER_TEMPLATE_NAME: &str = "closing_letter.md";
+
+/// Built-in closing letter, also seeded into each new matter's `templates/`.
+/// Rendered with the docgen context plus `extra.closed_on` and
+/// `extra.retain_until` (`YYYY-MM-DD`).
+pub const CLOSING_LETTER_TEMPLATE_BODY: &str = "\
+# Closing Letter
+
+{{ extra.closed_on | legal_date }}
+
+{{ client.name }}
+
+Re: {{ matter.matter_id }}
+
+Dear {{ client.name }},
+
+Our work on this matter is complete, and we have closed our file as of {{ extra.closed_on | legal_date }}.
+
+## Final account
+
+All fees and disbursements have been billed, and we hold no funds in trust for this matter.
+
+## Your file
+
+We will keep your file until {{ extra.retain_until | legal_date }}, after which it may be destroyed under our records retention policy. Please contact us before then if you would like any original documents returned.
+
+## Going forward
+
+We are no longer monitoring deadlines or taking steps on this matter. If a new issue arises, please contact us to discuss a new engagement.
+
+Thank you for the opportunity to assist you.
+";
+
+/// Years a closed file is kept when the matter's retention policy does not
+/// name a period.
+pub const DEFAULT_RETENTION_YEARS: u32 = 7;
+
+pub const MAX_RETENTION_YEARS: u32 = 100;
+
+/// Title of the deadline that marks the end of the retention period.
+pub const RETENTION_DEADLINE_TITLE: &str = "File retention period ends - review for destruction";
+
+/// Billing, docket, and trust facts the closeout checks are computed from.
+#[derive(Debug, Clone, Default)]
+pub struct CloseoutFacts {
+    pub unbilled_hours: Decimal,
+    pub unbilled_expenses: Decimal,
+    /// Titles of deadlines without a completion date.
+    pub open_deadlines: Vec<String>,
+    pub trust_balance: Decimal,
+}
+
+/// One required closeout step and whether the matter meets it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CloseoutCheck {
+    pub id: &'static str,
+    pub label: &'static str,
+    pub passed: bool,
+    pub detail: String,
+}
+
+pub fn closeout_checks(facts: &CloseoutFacts) -> Vec<CloseoutCheck> {
+    let unbilled = facts.unbilled_hours > Decimal::ZERO || facts.unbilled_expenses > Decimal::ZERO;
+    let open_deadlines = match facts.open_deadlines.as_slice() {
+        [] => "No open deadlines".to_string(),
+        titles => format!("{} open: {}", titles.len(), titles.join(", ")),
+    };
+    vec![
+        CloseoutCheck {
+            id: "unbilled_time",
+            label: "All time and expenses billed",
+            passed: !unbilled,
+            detail: if unbilled {
+                format!(
+                    "{} unbilled hours and {} in unbilled expenses",
+                    facts.unbilled_hours.normalize(),
+                    facts.unbilled_expenses.normalize()
+                )
+            } else {
+                "Nothing unbilled".to_string()
+            },
+        },
+        CloseoutCheck {
+            id: "open_deadlines",
+            label: "No open deadlines",
+            passed: facts.open_deadlines.is_empty(),
+            detail: open_deadlines,
+        },
+        CloseoutCheck {
+            id: "trust_balance",
+            label: "Trust balance is zero",
+            passed: facts.trust_balance.is_zero(),
+            detail: format!("Trust balance {}", facts.trust_balance.normalize()),
+        },
+    ]
+}
+
+/// The retention period named by a matter's `retention` policy, e.g.
+/// `"7 years"`, `"retain-10-years"`, or `"15y"`. `None` for policies such as
+/// `"follow-firm-policy"`.
+pub fn retention_years_from_policy(retention: &str) -> Option<u32> {
+    let lower = retention.to_ascii_lowercase();
+    let bytes = lower.as_bytes();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if !bytes[idx].is_ascii_digit() {
+            idx += 1;
+            continue;
+        }
+        let start = idx;
+        while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+            idx += 1;
+        }
+        let unit = lower[idx..].trim_start_matches([' ', '-', '_']);
+        if unit.starts_with('y')
+            && let Ok(years) = lower[start..idx].parse::<u32>()
+            && (1..=MAX_RETENTION_YEARS).contains(&years)
+        {
+            return Some(years);
+        }
+    }
+    None
+}
+
+pub fn retain_until(closed_at: DateTime<Utc>, years: u32) -> DateTime<Utc> {
+    closed_at
+        .checked_add_months(Months::new(years.saturating_mul(12)))
+        .unwrap_or(closed_at)
+}
+
+/// Markdown record of the closeout written to the matter's `closeout.md`.
+pub fn closeout_report(
+    matter_id: &str,
+    closed_at: DateTime<Utc>,
+    retain_until: DateTime<Utc>,
+    checks: &[CloseoutCheck],
+    closing_letter_path: &str,
+    closed_by: &str,
+    note: Option<&str>,
+) -> String {
+    let mut report = format!(
+        "# Matter Closeout\n\nMatter: {matter_id}\nClosed: {}\nClosed by: {closed_by}\nRetain until: {}\nClosing letter: {closing_letter_path}\n\n## Checks\n\n",
+        closed_at.date_naive(),
+        retain_until.date_naive()
+    );
+    for check in checks {
+        report.push_str(&format!(
+            "- [{}] {} — {}\n",
+            if check.passed { "x" } else { " " },
+            check.label,
+            check.detail
+        ));
+    }
+    if let Some(note) = note {
+        report.push_str(&format!("\n## Note\n\n{note}\n"));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn checks_flag_unbilled_time_open_deadlines_and_trust() {
+        let clean = closeout_checks(&CloseoutFacts::default());
+        assert!(clean.iter().all(|check| check.passed));
+
+        let checks = closeout_checks(&CloseoutFacts {
+            unbilled_hours: dec!(1.50),
+            unbilled_expenses: Decimal::ZERO,
+            open_deadlines: vec!["Reply brief".to_string()],
+            trust_balance: dec!(250.00),
+        });
+        assert!(checks.iter().all(|check| !check.passed));
+        assert_eq!(
+            checks[0].detail,
+            "1.5 unbilled hours and 0 in unbilled expenses"
+        );
+        assert_eq!(checks[1].detail, "1 open: Reply brief");
+        assert_eq!(checks[2].detail, "Trust balance 250");
+    }
+
+    #[test]
+    fn retention_policy_years_are_parsed() {
+        assert_eq!(retention_years_from_policy("7 years"), Some(7));
+        assert_eq!(retention_years_from_policy("retain-10-years"), Some(10));
+        assert_eq!(retention_years_from_policy("15y after close"), Some(15));
+        assert_eq!(retention_years_from_policy("follow-firm-policy"), None);
+        assert_eq!(retention_years_from_policy("30 days"), None);
+        assert_eq!(retention_years_from_policy("0 years"), None);
+    }
+
+    #[test]
+    fn retention_clock_and_letter_render() {
+        let closed = Utc.with_ymd_and_hms(2024, 2, 29, 12, 0, 0).unwrap();
+        assert_eq!(
+            retain_until(closed, 7).date_naive().to_string(),
+            "2031-02-28"
+        );
+
+        let context = serde_json::json!({
+            "matter": { "matter_id": "acme-v-foo" },
+            "client": { "name": "Acme Corp" },
+            "extra": { "closed_on": "2024-02-29", "retain_until": "2031-02-28" },
+        });
+        let letter = crate::legal::docgen::render_template(CLOSING_LETTER_TEMPLATE_BODY, &context)
+            .expect("closing letter renders");
+        assert!(letter.contains("Dear Acme Corp"));
+        assert!(letter.contains("until February 28, 2031"));
+    }
+}
@@ -0,0 +1,755 @@
+//! Comparison of two versions of a document (e.g. the last draft and the
+//! executed contract) and the change memo filed for the client.
+//!
+//! Both documents are split into paragraphs after removing differences that
+//! come from formatting or text extraction rather than from the parties:
+//! front matter, page headers and footers, hard line wraps, words hyphenated
+//! across lines, curly quotes and dashes, ligatures, and markdown emphasis.
+//! Paragraphs are then matched with an LCS diff. A changed paragraph whose
+//! words differ only in capitalization or punctuation is reported as a
+//! formatting change; anything else is substantive and gets a word-level
+//! redline plus notes on changed figures and obligation words.
+
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::Serialize;
+
+use crate::workspace::history::{DiffOp, diff};
+
+/// A deleted and an added paragraph in the same hunk are treated as one
+/// modified paragraph when this share of their words is shared.
+const MIN_PAIR_SIMILARITY: f64 = 0.5;
+
+/// Words whose addition or removal changes who must do what.
+const OBLIGATION_WORDS: [&str; 12] = [
+    "shall", "must", "will", "may", "not", "no", "never", "only", "unless", "except", "without",
+    "solely",
+];
+
+static PAGE_FURNITURE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(?:\[?page\s+\d+(?:\s+of\s+\d+)?\]?|-\s*\d+\s*-|\d{1,4}|\d+\s*/\s*\d+)$")
+        .expect("valid page furniture regex")
+});
+
+static BLOCK_START_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(?:#|[-*+]\s|\d+(?:\.\d+)*[.)]?\s|\([a-z0-9]+\)\s|(?:section|article)\s+\d)")
+        .expect("valid block start regex")
+});
+
+/// How a paragraph changed between the base and the compared document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Deleted,
+    Modified,
+    Moved,
+}
+
+impl ChangeKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Added => "Added",
+            Self::Deleted => "Deleted",
+            Self::Modified => "Modified",
+            Self::Moved => "Moved",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DocumentChange {
+    pub kind: ChangeKind,
+    /// False for capitalization, punctuation, and pure moves.
+    pub substantive: bool,
+    /// 1-based first line of the paragraph in the base document.
+    pub base_line: Option<usize>,
+    /// 1-based first line of the paragraph in the compared document.
+    pub compared_line: Option<usize>,
+    pub base_text: Option<String>,
+    pub compared_text: Option<String>,
+    /// Word-level redline for modified paragraphs: `[-removed-]{+added+}`.
+    pub redline: Option<String>,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DocumentComparison {
+    pub base_paragraphs: usize,
+    pub compared_paragraphs: usize,
+    pub unchanged: usize,
+    pub substantive: usize,
+    pub formatting: usize,
+    /// Changes in document order.
+    pub changes: Vec<DocumentChange>,
+}
+
+impl DocumentComparison {
+    pub fn identical(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// A normalized paragraph and where it starts.
+#[derive(Debug, Clone)]
+struct Paragraph {
+    line: usize,
+    text: String,
+}
+
+fn normalize_chars(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    for c in line.chars() {
+        match c {
+            '\u{201c}' | '\u{201d}' | '\u{201e}' | '\u{00ab}' | '\u{00bb}' => out.push('"'),
+            '\u{2018}' | '\u{2019}' | '\u{201a}' => out.push('\''),
+            '\u{2013}' | '\u{2014}' | '\u{2212}' => out.push('-'),
+            '\u{00a0}' | '\u{2002}' | '\u{2003}' | '\u{2009}' | '\t' => out.push(' '),
+            '\u{00ad}' | '\u{200b}' => {}
+            '\u{fb00}' => out.push_str("ff"),
+            '\u{fb01}' => out.push_str("fi"),
+            '\u{fb02}' => out.push_str("fl"),
+            '\u{fb03}' => out.push_str("ffi"),
+            '\u{fb04}' => out.push_str("ffl"),
+            _ => out.push(c),
+        }
+    }
+    let out = out.replace("**", "").replace("__", "").replace('`', "");
+    out.trim_start_matches(['#', '>'])
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split a document into normalized paragraphs.
+fn paragraphs(text: &str) -> Vec<Paragraph> {
+    let mut lines = text.lines().enumerate().peekable();
+    if lines.peek().is_some_and(|(_, line)| line.trim() == "---") {
+        lines.next();
+        for (_, line) in lines.by_ref() {
+            if line.trim() == "---" {
+                break;
+            }
+        }
+    }
+
+    let mut out: Vec<Paragraph> = Vec::new();
+    let mut current: Option<Paragraph> = None;
+    for (idx, raw) in lines {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            out.extend(current.take());
+            continue;
+        }
+        if PAGE_FURNITURE_RE.is_match(trimmed) || trimmed.starts_with("```") {
+            continue;
+        }
+        let line = normalize_chars(trimmed);
+        if line.is_empty() {
+            continue;
+        }
+        if BLOCK_START_RE.is_match(trimmed) {
+            out.extend(current.take());
+        }
+        match current.as_mut() {
+            Some(paragraph) => {
+                // Rejoin a word hyphenated across a line break ("exe-" / "cuted").
+                let hyphenated = paragraph.text.ends_with('-')
+                    && paragraph
+                        .text
+                        .chars()
+                        .rev()
+                        .nth(1)
+                        .is_some_and(char::is_alphabetic)
+                    && line.starts_with(|c: char| c.is_lowercase());
+                if hyphenated {
+                    paragraph.text.pop();
+                } else {
+                    paragraph.text.push(' ');
+                }
+                paragraph.text.push_str(&line);
+            }
+            None => {
+                current = Some(Paragraph {
+                    line: idx + 1,
+                    text: line,
+                });
+            }
+        }
+    }
+    out.extend(current);
+    out
+}
+
+/// Comparison key that ignores capitalization and punctuation, except
+/// punctuation inside figures (`1,000.50`, `5.2`).
+fn loose_key(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_alphanumeric() {
+            out.extend(c.to_lowercase());
+        } else if c.is_whitespace() {
+            if !out.is_empty() && !out.ends_with(' ') {
+                out.push(' ');
+            }
+        } else if c == '$'
+            || c == '%'
+            || (i > 0
+                && chars[i - 1].is_ascii_digit()
+                && chars.get(i + 1).is_some_and(char::is_ascii_digit))
+        {
+            out.push(c);
+        }
+    }
+    out.trim_end().to_string()
+}
+
+fn word_set(text: &str) -> HashSet<String> {
+    loose_key(text)
+        .split_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Dice similarity of the paragraphs' word sets.
+fn similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (word_set(a), word_set(b));
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    2.0 * a.intersection(&b).count() as f64 / (a.len() + b.len()) as f64
+}
+
+/// Word-level redline of `old` to `new` plus the removed and added words.
+fn word_redline(old: &str, new: &str) -> (String, Vec<String>, Vec<String>) {
+    let old_words: Vec<&str> = old.split_whitespace().collect();
+    let new_words: Vec<&str> = new.split_whitespace().collect();
+    fn flush(out: &mut Vec<String>, del: &mut Vec<&str>, ins: &mut Vec<&str>) {
+        if !del.is_empty() {
+            out.push(format!("[-{}-]", del.join(" ")));
+            del.clear();
+        }
+        if !ins.is_empty() {
+            out.push(format!("{{+{}+}}", ins.join(" ")));
+            ins.clear();
+        }
+    }
+
+    let mut out: Vec<String> = Vec::new();
+    let (mut removed, mut added) = (Vec::new(), Vec::new());
+    let (mut pending_del, mut pending_ins): (Vec<&str>, Vec<&str>) = (Vec::new(), Vec::new());
+    for op in diff(&old_words, &new_words) {
+        match op {
+            DiffOp::Equal(i, _) => {
+                flush(&mut out, &mut pending_del, &mut pending_ins);
+                out.push(old_words[i].to_string());
+            }
+            DiffOp::Delete(i) => {
+                pending_del.push(old_words[i]);
+                removed.push(old_words[i].to_string());
+            }
+            DiffOp::Insert(j) => {
+                pending_ins.push(new_words[j]);
+                added.push(new_words[j].to_string());
+            }
+        }
+    }
+    flush(&mut out, &mut pending_del, &mut pending_ins);
+    (out.join(" "), removed, added)
+}
+
+fn trim_word(word: &str) -> &str {
+    word.trim_matches(|c: char| !c.is_alphanumeric() && c != '$' && c != '%')
+}
+
+/// Notes on changed figures and obligation words.
+fn change_notes(removed: &[String], added: &[String]) -> Vec<String> {
+    let figures = |words: &[String]| -> Vec<String> {
+        words
+            .iter()
+            .map(|word| trim_word(word))
+            .filter(|word| word.chars().any(|c| c.is_ascii_digit()))
+            .map(str::to_string)
+            .collect()
+    };
+    let obligations = |words: &[String]| -> Vec<String> {
+        words
+            .iter()
+            .map(|word| trim_word(word).to_lowercase())
+            .filter(|word| OBLIGATION_WORDS.contains(&word.as_str()))
+            .collect()
+    };
+    let mut notes = Vec::new();
+    let (old_figures, new_figures) = (figures(removed), figures(added));
+    if !old_figures.is_empty() || !new_figures.is_empty() {
+        notes.push(format!(
+            "Figures changed: {} → {}",
+            if old_figures.is_empty() {
+                "(none)".to_string()
+            } else {
+                old_figures.join(", ")
+            },
+            if new_figures.is_empty() {
+                "(none)".to_string()
+            } else {
+                new_figures.join(", ")
+            }
+        ));
+    }
+    let (old_words, new_words) = (obligations(removed), obligations(added));
+    if old_words != new_words && (!old_words.is_empty() || !new_words.is_empty()) {
+        notes.push(format!(
+            "Obligation wording changed: {} → {}",
+            if old_words.is_empty() {
+                "(none)".to_string()
+            } else {
+                old_words.join(", ")
+            },
+            if new_words.is_empty() {
+                "(none)".to_string()
+            } else {
+                new_words.join(", ")
+            }
+        ));
+    }
+    notes
+}
+
+fn modified_change(base: &Paragraph, compared: &Paragraph) -> DocumentChange {
+    let substantive = loose_key(&base.text) != loose_key(&compared.text);
+    let (redline, removed, added) = word_redline(&base.text, &compared.text);
+    let notes = if substantive {
+        change_notes(&removed, &added)
+    } else {
+        vec!["Capitalization or punctuation only".to_string()]
+    };
+    DocumentChange {
+        kind: ChangeKind::Modified,
+        substantive,
+        base_line: Some(base.line),
+        compared_line: Some(compared.line),
+        base_text: Some(base.text.clone()),
+        compared_text: Some(compared.text.clone()),
+        redline: Some(redline),
+        notes,
+    }
+}
+
+fn one_sided_change(kind: ChangeKind, paragraph: &Paragraph) -> DocumentChange {
+    let (base_line, compared_line, base_text, compared_text) = match kind {
+        ChangeKind::Deleted => (
+            Some(paragraph.line),
+            None,
+            Some(paragraph.text.clone()),
+            None,
+        ),
+        _ => (
+            None,
+            Some(paragraph.line),
+            None,
+            Some(paragraph.text.clone()),
+        ),
+    };
+    let words: Vec<String> = paragraph
+        .text
+        .split_whitespace()
+        .map(str::to_string)
+        .collect();
+    let notes = match kind {
+        ChangeKind::Deleted => change_notes(&words, &[]),
+        _ => change_notes(&[], &words),
+    };
+    DocumentChange {
+        kind,
+        substantive: !loose_key(&paragraph.text).is_empty(),
+        base_line,
+        compared_line,
+        base_text,
+        compared_text,
+        redline: None,
+        notes,
+    }
+}
+
+/// Compare `base` (e.g. the last draft) with `compared` (e.g. the
+/// executed copy).
+pub fn compare_documents(base: &str, compared: &str) -> DocumentComparison {
+    let base_paragraphs = paragraphs(base);
+    let compared_paragraphs = paragraphs(compared);
+    let base_keys: Vec<&str> = base_paragraphs.iter().map(|p| p.text.as_str()).collect();
+    let compared_keys: Vec<&str> = compared_paragraphs
+        .iter()
+        .map(|p| p.text.as_str())
+        .collect();
+    let ops = diff(&base_keys, &compared_keys);
+
+    // Group consecutive deletions and insertions into hunks.
+    let mut hunks: Vec<(Vec<usize>, Vec<usize>)> = Vec::new();
+    let mut unchanged = 0;
+    let mut open = false;
+    for op in &ops {
+        match *op {
+            DiffOp::Equal(..) => {
+                unchanged += 1;
+                open = false;
+            }
+            DiffOp::Delete(i) => {
+                if !open {
+                    hunks.push((Vec::new(), Vec::new()));
+                    open = true;
+                }
+                if let Some(hunk) = hunks.last_mut() {
+                    hunk.0.push(i);
+                }
+            }
+            DiffOp::Insert(j) => {
+                if !open {
+                    hunks.push((Vec::new(), Vec::new()));
+                    open = true;
+                }
+                if let Some(hunk) = hunks.last_mut() {
+                    hunk.1.push(j);
+                }
+            }
+        }
+    }
+
+    // Pair deletions with similar insertions inside each hunk.
+    let mut paired: Vec<Vec<(usize, Option<usize>)>> = Vec::new();
+    let mut unpaired_inserts: Vec<usize> = Vec::new();
+    for (deleted, inserted) in &hunks {
+        let mut taken = vec![false; inserted.len()];
+        let mut hunk_pairs = Vec::new();
+        for &i in deleted {
+            let best = inserted
+                .iter()
+                .enumerate()
+                .filter(|(k, _)| !taken[*k])
+                .map(|(k, &j)| {
+                    (
+                        k,
+                        similarity(&base_paragraphs[i].text, &compared_paragraphs[j].text),
+                    )
+                })
+                .filter(|(_, score)| *score >= MIN_PAIR_SIMILARITY)
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            match best {
+                Some((k, _)) => {
+                    taken[k] = true;
+                    hunk_pairs.push((i, Some(inserted[k])));
+                }
+                None => hunk_pairs.push((i, None)),
+            }
+        }
+        unpaired_inserts.extend(
+            inserted
+                .iter()
+                .zip(&taken)
+                .filter(|(_, taken)| !**taken)
+                .map(|(&j, _)| j),
+        );
+        paired.push(hunk_pairs);
+    }
+
+    // A deleted paragraph that reappears word for word elsewhere was moved.
+    let mut moved_to: Vec<(usize, usize)> = Vec::new();
+    for &(i, _) in paired.iter().flatten().filter(|(_, j)| j.is_none()) {
+        if let Some(pos) = unpaired_inserts
+            .iter()
+            .position(|&j| compared_paragraphs[j].text == base_paragraphs[i].text)
+        {
+            moved_to.push((i, unpaired_inserts.remove(pos)));
+        }
+    }
+
+    let mut changes = Vec::new();
+    for (hunk, (_, inserted)) in paired.iter().zip(&hunks) {
+        for &(i, j) in hunk {
+            let base = &base_paragraphs[i];
+            let change = match (j, moved_to.iter().find(|(from, _)| *from == i)) {
+                (Some(j), _) => modified_change(base, &compared_paragraphs[j]),
+                (None, Some(&(_, to))) => DocumentChange {
+                    kind: ChangeKind::Moved,
+                    substantive: false,
+                    base_line: Some(base.line),
+                    compared_line: Some(compared_paragraphs[to].line),
+                    base_text: Some(base.text.clone()),
+                    compared_text: None,
+                    redline: None,
+                    notes: vec!["Paragraph moved without changes".to_string()],
+                },
+                (None, None) => one_sided_change(ChangeKind::Deleted, base),
+            };
+            changes.push(change);
+        }
+        for &j in inserted.iter().filter(|j| unpaired_inserts.contains(*j)) {
+            changes.push(one_sided_change(ChangeKind::Added, &compared_paragraphs[j]));
+        }
+    }
+
+    let substantive = changes.iter().filter(|change| change.substantive).count();
+    DocumentComparison {
+        base_paragraphs: base_paragraphs.len(),
+        compared_paragraphs: compared_paragraphs.len(),
+        unchanged,
+        substantive,
+        formatting: changes.len() - substantive,
+        changes,
+    }
+}
+
+/// Memo title naming both files: `Changes from lease-draft.md to lease.md`.
+pub fn default_memo_title(base_path: &str, compared_path: &str) -> String {
+    let name = |path: &str| path.rsplit('/').next().unwrap_or(path).to_string();
+    format!(
+        "Changes from {} to {}",
+        name(base_path),
+        name(compared_path)
+    )
+}
+
+/// Who and what a change memo describes.
+#[derive(Debug, Clone)]
+pub struct ChangeMemo<'a> {
+    pub matter_id: &'a str,
+    pub title: &'a str,
+    pub base_path: &'a str,
+    pub compared_path: &'a str,
+    pub prepared_by: &'a str,
+    pub prepared_at: DateTime<Utc>,
+}
+
+fn line_ref(change: &DocumentChange) -> String {
+    match (change.base_line, change.compared_line) {
+        (Some(base), Some(compared)) => format!("base line {base}, compared line {compared}"),
+        (Some(base), None) => format!("base line {base}"),
+        (None, Some(compared)) => format!("compared line {compared}"),
+        (None, None) => String::new(),
+    }
+}
+
+fn quote(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("> {line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render the change memo for the client file.
+pub fn render_change_memo(memo: &ChangeMemo<'_>, comparison: &DocumentComparison) -> String {
+    let mut out = format!(
+        "# Memo: {}\n\n\
+         > **Privileged & confidential — attorney work product.** Automated comparison; \
+         confirm each change against the signed original before relying on it.\n\n\
+         - **Matter:** {}\n\
+         - **Base document:** `{}`\n\
+         - **Compared document:** `{}`\n\
+         - **Prepared by:** {}\n\
+         - **Date:** {}\n\n\
+         ## Summary\n\n",
+        memo.title,
+        memo.matter_id,
+        memo.base_path,
+        memo.compared_path,
+        memo.prepared_by,
+        memo.prepared_at.format("%Y-%m-%d %H:%M UTC"),
+    );
+    if comparison.identical() {
+        out.push_str("The documents match. No substantive or formatting differences were found.\n");
+    } else {
+        out.push_str(&format!(
+            "- Substantive changes: {}\n- Formatting-only differences: {}\n- Paragraphs unchanged: {}\n",
+            comparison.substantive, comparison.formatting, comparison.unchanged
+        ));
+    }
+
+    let substantive: Vec<&DocumentChange> = comparison
+        .changes
+        .iter()
+        .filter(|change| change.substantive)
+        .collect();
+    if !substantive.is_empty() {
+        out.push_str("\n## Substantive changes\n");
+        for (idx, change) in substantive.iter().enumerate() {
+            out.push_str(&format!(
+                "\n### {}. {} ({})\n\n",
+                idx + 1,
+                change.kind.label(),
+                line_ref(change)
+            ));
+            match change.kind {
+                ChangeKind::Modified => {
+                    out.push_str(&format!(
+                        "**Base:**\n\n{}\n\n**Compared:**\n\n{}\n\n**Redline:** {}\n",
+                        quote(change.base_text.as_deref().unwrap_or_default()),
+                        quote(change.compared_text.as_deref().unwrap_or_default()),
+                        change.redline.as_deref().unwrap_or_default()
+                    ));
+                }
+                _ => {
+                    let text = change
+                        .base_text
+                        .as_deref()
+                        .or(change.compared_text.as_deref())
+                        .unwrap_or_default();
+                    out.push_str(&format!("{}\n", quote(text)));
+                }
+            }
+            for note in &change.notes {
+                out.push_str(&format!("\n- {note}"));
+            }
+            if !change.notes.is_empty() {
+                out.push('\n');
+            }
+        }
+    }
+
+    let formatting: Vec<&DocumentChange> = comparison
+        .changes
+        .iter()
+        .filter(|change| !change.substantive)
+        .collect();
+    if !formatting.is_empty() {
+        out.push_str("\n## Formatting-only differences\n\n");
+        for change in formatting {
+            let detail = change
+                .redline
+                .as_deref()
+                .or(change.base_text.as_deref())
+                .or(change.compared_text.as_deref())
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "- {} ({}): {}\n",
+                change.kind.label(),
+                line_ref(change),
+                detail
+            ));
+        }
+    }
+
+    out.push_str(
+        "\n## Method\n\n\
+         Paragraphs were compared after removing page headers and footers, line wrapping, \
+         words hyphenated across lines, quote and dash styles, ligatures, and markdown \
+         formatting. Changes limited to capitalization or punctuation are listed as \
+         formatting. When the compared document is text extracted from a signed PDF, check \
+         figures, signature blocks, and schedules against the PDF itself.\n",
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const DRAFT: &str = "\
+---
+status: draft
+---
+
+# Services Agreement
+
+1. Fees. Client shall pay the Provider a monthly fee of $5,000 within
+thirty days of each invoice.
+
+2. Term. This agreement continues for one year.
+
+3. Notices. Notices must be in writing.
+
+4. Assignment. Neither party may assign this agreement without consent.
+";
+
+    const EXECUTED: &str = "\
+SERVICES AGREEMENT
+
+1. Fees. Client shall pay the Provider a monthly fee of $7,500 within
+thirty days of each in-
+voice.
+
+Page 1 of 2
+\u{c}
+2. Term. This Agreement continues for one year.
+
+4. Assignment. Neither party may assign this agreement without consent.
+
+3. Notices. Notices must be in writing.
+
+5. Governing law. This agreement is governed by the laws of New York.
+";
+
+    #[test]
+    fn separates_substantive_changes_from_formatting_noise() {
+        let comparison = compare_documents(DRAFT, EXECUTED);
+        let summary: Vec<(ChangeKind, bool)> = comparison
+            .changes
+            .iter()
+            .map(|change| (change.kind, change.substantive))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ChangeKind::Modified, false),
+                (ChangeKind::Modified, true),
+                (ChangeKind::Modified, false),
+                (ChangeKind::Moved, false),
+                (ChangeKind::Added, true),
+            ],
+            "{comparison:#?}"
+        );
+        assert_eq!(comparison.substantive, 2);
+        assert_eq!(comparison.formatting, 3);
+
+        let fees = &comparison.changes[1];
+        assert_eq!(fees.base_line, Some(7));
+        assert_eq!(fees.compared_line, Some(3));
+        assert!(
+            fees.redline
+                .as_deref()
+                .unwrap()
+                .contains("[-$5,000-] {+$7,500+}"),
+            "{fees:?}"
+        );
+        assert_eq!(fees.notes, vec!["Figures changed: $5,000 → $7,500"]);
+
+        let moved = &comparison.changes[3];
+        assert_eq!(moved.base_line, Some(12));
+        assert_eq!(moved.compared_line, Some(13));
+        assert_eq!(comparison.changes[4].compared_line, Some(15));
+    }
+
+    #[test]
+    fn notes_obligation_words_and_renders_memo() {
+        let comparison = compare_documents(
+            "The tenant shall repair the roof.",
+            "The tenant may repair the roof.",
+        );
+        assert_eq!(
+            comparison.changes[0].notes,
+            vec!["Obligation wording changed: shall → may"]
+        );
+
+        let memo = render_change_memo(
+            &ChangeMemo {
+                matter_id: "acme",
+                title: "Executed lease vs. last draft",
+                base_path: "matters/acme/drafts/lease.md",
+                compared_path: "matters/acme/executed/lease.md",
+                prepared_by: "jane",
+                prepared_at: Utc.with_ymd_and_hms(2026, 10, 15, 9, 0, 0).unwrap(),
+            },
+            &comparison,
+        );
+        assert!(memo.starts_with("# Memo: Executed lease vs. last draft\n"));
+        assert!(memo.contains("- Substantive changes: 1\n"));
+        assert!(memo.contains("**Redline:** The tenant [-shall-] {+may+} repair the roof."));
+        assert!(memo.contains("- Obligation wording changed: shall → may"));
+
+        let same = compare_documents("Same text.", "Same   text.\n\nPage 3");
+        assert!(same.identical());
+    }
+}
@@ -0,0 +1,697 @@
+//! Matter correspondence drafted by the `compose_email` tool.
+//!
+//! Drafts are plain workspace documents under the matter's
+//! `communications/outbox/` folder, so attorneys can read and edit them
+//! like any other file. Sending re-reads the file (picking up any edits),
+//! hands it to the email tool only after explicit approval, moves the sent
+//! copy to `communications/sent/`, and appends a row to
+//! `communications/contact_log.md`.
+//!
+//! Voice notes transcribed on messaging channels are filed under
+//! `communications/voice/` with a pointer to the original audio.
+//!
+//! Inbound email delivered to the email intake webhook is filed under
+//! `communications/inbox/` and logged the same way.
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::legal::markdown::table_cell;
+
+/// Longest slug used in draft file names.
+const MAX_SLUG_CHARS: usize = 48;
+
+/// Separates the header block from the message body.
+const BODY_SEPARATOR: &str = "\n---\n\n";
+
+/// Longest message excerpt kept in a chat contact log row.
+const MAX_LOG_EXCERPT_CHARS: usize = 200;
+
+/// Header written when a matter has no contact log yet (matches the matter
+/// scaffold).
+pub const CONTACT_LOG_TEMPLATE: &str = "# Communications Log\n\n| Date | With | Channel | Summary | Follow-up |\n|---|---|---|---|---|\n";
+
+/// Where an email draft is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailStatus {
+    Draft,
+    Sent,
+    /// Approved but the email tool reported an error; the draft stays in
+    /// the outbox and can be sent again.
+    DeliveryFailed,
+}
+
+impl EmailStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Sent => "sent",
+            Self::DeliveryFailed => "delivery_failed",
+        }
+    }
+
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim() {
+            "draft" => Some(Self::Draft),
+            "sent" => Some(Self::Sent),
+            "delivery_failed" => Some(Self::DeliveryFailed),
+            _ => None,
+        }
+    }
+}
+
+/// An outgoing email stored as a matter document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailDraft {
+    pub matter_id: String,
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    pub subject: String,
+    pub body: String,
+    pub status: EmailStatus,
+    pub drafted_by: String,
+    /// Kept as rendered text so a parse/render round trip is lossless.
+    pub drafted_at: String,
+    pub sent_at: Option<String>,
+    pub approved_by: Option<String>,
+    pub delivery_error: Option<String>,
+}
+
+fn slug(subject: &str) -> String {
+    let mut out = String::new();
+    for c in subject.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+        if out.len() >= MAX_SLUG_CHARS {
+            break;
+        }
+    }
+    let out = out.trim_end_matches('-');
+    if out.is_empty() {
+        "email".to_string()
+    } else {
+        out.to_string()
+    }
+}
+
+/// Format a timestamp the way draft headers store it.
+pub fn format_timestamp(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%d %H:%M UTC").to_string()
+}
+
+/// `matters/acme/communications/outbox/2026-10-14-153000-settlement-offer.md`.
+pub fn outbox_path(
+    matter_root: &str,
+    matter_id: &str,
+    drafted_at: DateTime<Utc>,
+    subject: &str,
+) -> String {
+    format!(
+        "{}/{matter_id}/communications/outbox/{}-{}.md",
+        matter_root.trim_matches('/'),
+        drafted_at.format("%Y-%m-%d-%H%M%S"),
+        slug(subject)
+    )
+}
+
+/// Sent-copy path for an outbox draft, or `None` if `path` is not in an
+/// outbox folder.
+pub fn sent_path(outbox_path: &str) -> Option<String> {
+    let (prefix, name) = outbox_path.rsplit_once("/communications/outbox/")?;
+    if name.is_empty() || name.contains('/') {
+        return None;
+    }
+    Some(format!("{prefix}/communications/sent/{name}"))
+}
+
+/// Contact log path for a matter.
+pub fn contact_log_path(matter_root: &str, matter_id: &str) -> String {
+    format!(
+        "{}/{matter_id}/communications/contact_log.md",
+        matter_root.trim_matches('/')
+    )
+}
+
+/// Split a comma/semicolon separated address list.
+pub fn parse_addresses(raw: &str) -> Vec<String> {
+    raw.split([',', ';'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Minimal shape check; the email tool does the real validation.
+pub fn is_plausible_address(addr: &str) -> bool {
+    match addr.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !addr.chars().any(|c| c.is_whitespace() || c == '|')
+        }
+        None => false,
+    }
+}
+
+/// Header values are single lines; collapse anything that is not.
+fn header_value(raw: &str) -> String {
+    raw.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Render a draft as a workspace document.
+pub fn render_email_draft(draft: &EmailDraft) -> String {
+    let mut out = format!(
+        "# Email: {}\n\n\
+         - **Matter:** {}\n\
+         - **To:** {}\n",
+        header_value(&draft.subject),
+        draft.matter_id,
+        draft.to.join(", "),
+    );
+    if !draft.cc.is_empty() {
+        out.push_str(&format!("- **Cc:** {}\n", draft.cc.join(", ")));
+    }
+    out.push_str(&format!(
+        "- **Subject:** {}\n\
+         - **Status:** {}\n\
+         - **Drafted by:** {}\n\
+         - **Drafted:** {}\n",
+        header_value(&draft.subject),
+        draft.status.as_str(),
+        header_value(&draft.drafted_by),
+        draft.drafted_at,
+    ));
+    if let Some(approved_by) = draft.approved_by.as_deref() {
+        out.push_str(&format!(
+            "- **Approved by:** {}\n",
+            header_value(approved_by)
+        ));
+    }
+    if let Some(sent_at) = draft.sent_at.as_deref() {
+        out.push_str(&format!("- **Sent:** {sent_at}\n"));
+    }
+    if let Some(error) = draft.delivery_error.as_deref() {
+        out.push_str(&format!("- **Delivery error:** {}\n", header_value(error)));
+    }
+    out.push_str(BODY_SEPARATOR);
+    out.push_str(draft.body.trim());
+    out.push('\n');
+    out
+}
+
+/// Parse a document written by [`render_email_draft`], including one an
+/// attorney has since edited.
+pub fn parse_email_draft(content: &str) -> Option<EmailDraft> {
+    let (header, body) = content.split_once(BODY_SEPARATOR)?;
+    let field = |name: &str| {
+        let marker = format!("- **{name}:**");
+        header
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(marker.as_str()))
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    Some(EmailDraft {
+        matter_id: field("Matter")?,
+        to: parse_addresses(&field("To")?),
+        cc: field("Cc").map(|v| parse_addresses(&v)).unwrap_or_default(),
+        subject: field("Subject")?,
+        body: body.trim().to_string(),
+        status: EmailStatus::parse(&field("Status")?)?,
+        drafted_by: field("Drafted by").unwrap_or_default(),
+        drafted_at: field("Drafted").unwrap_or_default(),
+        sent_at: field("Sent"),
+        approved_by: field("Approved by"),
+        delivery_error: field("Delivery error"),
+    })
+}
+
+/// Contact log row for a send attempt.
+pub fn contact_log_row(draft: &EmailDraft, at: DateTime<Utc>, document_path: &str) -> String {
+    let summary = match draft.status {
+        EmailStatus::Sent => format!("Sent \"{}\" ({document_path})", draft.subject),
+        EmailStatus::DeliveryFailed => format!(
+            "Delivery failed for \"{}\": {} ({document_path})",
+            draft.subject,
+            draft.delivery_error.as_deref().unwrap_or("unknown error")
+        ),
+        EmailStatus::Draft => format!("Drafted \"{}\" ({document_path})", draft.subject),
+    };
+    let follow_up = match draft.status {
+        EmailStatus::DeliveryFailed => "Resend or contact by other means",
+        _ => "",
+    };
+    format!(
+        "| {} | {} | Email | {} | {} |\n",
+        at.format("%Y-%m-%d"),
+        table_cell(&draft.to.join(", ")),
+        table_cell(&summary),
+        follow_up
+    )
+}
+
+/// Contact log row for a chat message exchanged on a messaging channel
+/// (e.g. a matter's Signal group). Long messages are cut to an excerpt.
+pub fn channel_message_row(
+    at: DateTime<Utc>,
+    with: &str,
+    channel: &str,
+    inbound: bool,
+    text: &str,
+) -> String {
+    let direction = if inbound { "Received" } else { "Sent" };
+    format!(
+        "| {} | {} | {} | {} |  |\n",
+        at.format("%Y-%m-%d %H:%M"),
+        table_cell(with),
+        channel,
+        table_cell(&format!("{direction} \"{}\"", log_excerpt(text)))
+    )
+}
+
+fn log_excerpt(text: &str) -> String {
+    let flat = header_value(text);
+    let mut excerpt: String = flat.chars().take(MAX_LOG_EXCERPT_CHARS).collect();
+    if excerpt.len() < flat.len() {
+        excerpt.push('…');
+    }
+    excerpt
+}
+
+/// `matters/acme/communications/voice/2026-10-14-153000-telegram.md`.
+pub fn voice_note_path(
+    matter_root: &str,
+    matter_id: &str,
+    received_at: DateTime<Utc>,
+    channel: &str,
+) -> String {
+    format!(
+        "{}/{matter_id}/communications/voice/{}-{}.md",
+        matter_root.trim_matches('/'),
+        received_at.format("%Y-%m-%d-%H%M%S"),
+        slug(channel)
+    )
+}
+
+/// Transcript document for a voice note received on a messaging channel.
+/// The audio itself stays with the channel; `audio_ref` says where.
+pub fn render_voice_note(
+    channel: &str,
+    from: &str,
+    received_at: DateTime<Utc>,
+    mime_type: &str,
+    audio_ref: &str,
+    transcript: &str,
+) -> String {
+    format!(
+        "# Voice note\n\nChannel: {}\nFrom: {}\nReceived: {}\nFormat: {}\nAudio: {}{BODY_SEPARATOR}{}\n",
+        header_value(channel),
+        header_value(from),
+        format_timestamp(received_at),
+        header_value(mime_type),
+        header_value(audio_ref),
+        transcript.trim()
+    )
+}
+
+/// Contact log row pointing at a transcribed voice note.
+pub fn voice_note_row(
+    at: DateTime<Utc>,
+    with: &str,
+    channel: &str,
+    transcript: &str,
+    document_path: &str,
+) -> String {
+    format!(
+        "| {} | {} | {} | {} |  |\n",
+        at.format("%Y-%m-%d %H:%M"),
+        table_cell(with),
+        table_cell(channel),
+        table_cell(&format!(
+            "Voice note \"{}\" ({document_path})",
+            log_excerpt(transcript)
+        ))
+    )
+}
+
+/// An email received through the inbound email webhook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InboundEmail {
+    pub from: String,
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    pub subject: String,
+    pub body: String,
+    pub received_at: DateTime<Utc>,
+    pub message_id: Option<String>,
+}
+
+/// `matters/acme/communications/inbox/2026-10-14-153000-settlement-offer.md`.
+pub fn inbox_path(
+    matter_root: &str,
+    matter_id: &str,
+    received_at: DateTime<Utc>,
+    subject: &str,
+) -> String {
+    format!(
+        "{}/{matter_id}/communications/inbox/{}-{}.md",
+        matter_root.trim_matches('/'),
+        received_at.format("%Y-%m-%d-%H%M%S"),
+        slug(subject)
+    )
+}
+
+/// Folder for inbound email that names no existing matter.
+pub const UNFILED_EMAIL_DIR: &str = "email/unfiled";
+
+/// `email/unfiled/2026-10-14-153000-settlement-offer.md`.
+pub fn unfiled_email_path(received_at: DateTime<Utc>, subject: &str) -> String {
+    format!(
+        "{UNFILED_EMAIL_DIR}/{}-{}.md",
+        received_at.format("%Y-%m-%d-%H%M%S"),
+        slug(subject)
+    )
+}
+
+/// Render an inbound email as a workspace document. `attachments` lists
+/// where each filed attachment went; `skipped` names attachments that were
+/// not filed and why.
+pub fn render_inbound_email(
+    email: &InboundEmail,
+    matter_id: Option<&str>,
+    attachments: &[String],
+    skipped: &[String],
+) -> String {
+    let mut out = format!(
+        "# Email: {}\n\n\
+         - **Matter:** {}\n\
+         - **From:** {}\n\
+         - **To:** {}\n",
+        header_value(&email.subject),
+        matter_id.unwrap_or("unfiled"),
+        header_value(&email.from),
+        email.to.join(", "),
+    );
+    if !email.cc.is_empty() {
+        out.push_str(&format!("- **Cc:** {}\n", email.cc.join(", ")));
+    }
+    out.push_str(&format!(
+        "- **Subject:** {}\n\
+         - **Status:** received\n\
+         - **Received:** {}\n",
+        header_value(&email.subject),
+        format_timestamp(email.received_at),
+    ));
+    if let Some(message_id) = email.message_id.as_deref() {
+        out.push_str(&format!("- **Message-ID:** {}\n", header_value(message_id)));
+    }
+    for path in attachments {
+        out.push_str(&format!("- **Attachment:** {path}\n"));
+    }
+    for note in skipped {
+        out.push_str(&format!(
+            "- **Attachment not filed:** {}\n",
+            header_value(note)
+        ));
+    }
+    out.push_str(BODY_SEPARATOR);
+    out.push_str(email.body.trim());
+    out.push('\n');
+    out
+}
+
+/// Contact log row for a filed inbound email.
+pub fn inbound_email_row(email: &InboundEmail, document_path: &str) -> String {
+    format!(
+        "| {} | {} | Email | {} |  |\n",
+        email.received_at.format("%Y-%m-%d %H:%M"),
+        table_cell(&email.from),
+        table_cell(&format!("Received \"{}\" ({document_path})", email.subject))
+    )
+}
+
+/// A row read back from a matter's contact log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContactLogEntry {
+    pub date: NaiveDate,
+    pub with: String,
+    pub channel: String,
+    pub summary: String,
+}
+
+/// Split a markdown table row into cells, honouring `\|` escapes.
+fn split_table_row(line: &str) -> Vec<String> {
+    let inner = line.trim().trim_start_matches('|');
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '|' => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+/// Parse the dated rows of a contact log. Rows whose first cell does not
+/// start with a `YYYY-MM-DD` date (the header, separators, hand-written
+/// notes) are skipped.
+pub fn parse_contact_log(content: &str) -> Vec<ContactLogEntry> {
+    content
+        .lines()
+        .filter(|line| line.trim_start().starts_with('|'))
+        .filter_map(|line| {
+            let cells = split_table_row(line);
+            let date = NaiveDate::parse_from_str(cells.first()?.get(..10)?, "%Y-%m-%d").ok()?;
+            Some(ContactLogEntry {
+                date,
+                with: cells.get(1).cloned().unwrap_or_default(),
+                channel: cells.get(2).cloned().unwrap_or_default(),
+                summary: cells.get(3).cloned().unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Parameters for the email tool's `send_message` action.
+pub fn send_params(draft: &EmailDraft) -> serde_json::Value {
+    let mut params = crate::legal::status_report::email_params(
+        &draft.to.join(", "),
+        &draft.subject,
+        &draft.body,
+    );
+    if !draft.cc.is_empty() {
+        params["cc"] = serde_json::json!(draft.cc.join(", "));
+    }
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn draft() -> EmailDraft {
+        EmailDraft {
+            matter_id: "acme-v-foo".to_string(),
+            to: vec!["client@example.com".to_string()],
+            cc: vec!["partner@firm.example".to_string()],
+            subject: "Settlement offer".to_string(),
+            body: "Dear Client,\n\n---\n\nThe other side has offered $50,000.".to_string(),
+            status: EmailStatus::Draft,
+            drafted_by: "agent".to_string(),
+            drafted_at: "2026-10-14 15:30 UTC".to_string(),
+            sent_at: None,
+            approved_by: None,
+            delivery_error: None,
+        }
+    }
+
+    #[test]
+    fn paths_are_dated_and_move_from_outbox_to_sent() {
+        let at = Utc.with_ymd_and_hms(2026, 10, 14, 15, 30, 0).unwrap();
+        let path = outbox_path("/matters/", "acme-v-foo", at, "Re: Settlement offer!");
+        assert_eq!(
+            path,
+            "matters/acme-v-foo/communications/outbox/2026-10-14-153000-re-settlement-offer.md"
+        );
+        assert_eq!(
+            sent_path(&path).as_deref(),
+            Some("matters/acme-v-foo/communications/sent/2026-10-14-153000-re-settlement-offer.md")
+        );
+        assert_eq!(sent_path("matters/acme-v-foo/memos/x.md"), None);
+        assert_eq!(
+            sent_path("matters/acme-v-foo/communications/outbox/nested/x.md"),
+            None
+        );
+    }
+
+    #[test]
+    fn draft_round_trips_and_keeps_body_separators() {
+        let mut draft = draft();
+        assert_eq!(
+            parse_email_draft(&render_email_draft(&draft)),
+            Some(draft.clone())
+        );
+
+        draft.status = EmailStatus::Sent;
+        draft.approved_by = Some("jane".to_string());
+        draft.sent_at = Some("2026-10-15 09:00 UTC".to_string());
+        let rendered = render_email_draft(&draft);
+        assert!(rendered.contains("- **Status:** sent\n"));
+        assert_eq!(parse_email_draft(&rendered), Some(draft));
+        assert_eq!(parse_email_draft("# Email: no header"), None);
+    }
+
+    #[test]
+    fn contact_log_rows_escape_cells_and_flag_failures() {
+        let at = Utc.with_ymd_and_hms(2026, 10, 15, 9, 0, 0).unwrap();
+        let mut draft = draft();
+        draft.subject = "A | B".to_string();
+        draft.status = EmailStatus::DeliveryFailed;
+        draft.delivery_error = Some("quota exceeded".to_string());
+        let row = contact_log_row(&draft, at, "matters/acme-v-foo/communications/outbox/x.md");
+        assert!(row.starts_with("| 2026-10-15 | client@example.com | Email | Delivery failed"));
+        assert!(row.contains("A \\| B"));
+        assert!(row.contains("quota exceeded"));
+        assert!(row.ends_with("| Resend or contact by other means |\n"));
+    }
+
+    #[test]
+    fn contact_log_rows_parse_back() {
+        let at = Utc.with_ymd_and_hms(2026, 10, 15, 9, 0, 0).unwrap();
+        let mut draft = draft();
+        draft.subject = "A | B".to_string();
+        draft.status = EmailStatus::Sent;
+        let mut log = CONTACT_LOG_TEMPLATE.to_string();
+        log.push_str(&contact_log_row(&draft, at, "matters/acme-v-foo/x.md"));
+        log.push_str(&channel_message_row(
+            at,
+            "+15550100",
+            "signal",
+            true,
+            "Call me",
+        ));
+        log.push_str("| TBD | someone | Phone | undated note |  |\n");
+
+        let entries = parse_contact_log(&log);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].date, at.date_naive());
+        assert_eq!(entries[0].channel, "Email");
+        assert_eq!(
+            entries[0].summary,
+            "Sent \"A | B\" (matters/acme-v-foo/x.md)"
+        );
+        assert_eq!(entries[1].with, "+15550100");
+        assert_eq!(entries[1].summary, "Received \"Call me\"");
+    }
+
+    #[test]
+    fn channel_message_rows_flatten_and_truncate() {
+        let at = Utc.with_ymd_and_hms(2026, 10, 15, 9, 30, 0).unwrap();
+        let row = channel_message_row(at, "Jane | Client", "Signal", true, "Call me\nafter 5");
+        assert_eq!(
+            row,
+            "| 2026-10-15 09:30 | Jane \\| Client | Signal | Received \"Call me after 5\" |  |\n"
+        );
+
+        let long = "x".repeat(500);
+        let row = channel_message_row(at, "+15551234567", "Signal", false, &long);
+        assert!(row.contains(&format!("Sent \"{}…\"", "x".repeat(MAX_LOG_EXCERPT_CHARS))));
+    }
+
+    #[test]
+    fn voice_notes_render_with_audio_reference() {
+        let at = Utc.with_ymd_and_hms(2026, 10, 15, 9, 30, 5).unwrap();
+        let path = voice_note_path("/matters/", "acme-v-foo", at, "Telegram");
+        assert_eq!(
+            path,
+            "matters/acme-v-foo/communications/voice/2026-10-15-093005-telegram.md"
+        );
+
+        let doc = render_voice_note(
+            "telegram",
+            "Jane\nClient",
+            at,
+            "audio/ogg",
+            "telegram:file/AwACAgQ",
+            "  Please call me about the lease.  ",
+        );
+        assert!(doc.starts_with("# Voice note\n\nChannel: telegram\nFrom: Jane Client\n"));
+        assert!(
+            doc.contains("Audio: telegram:file/AwACAgQ\n---\n\nPlease call me about the lease.\n")
+        );
+
+        let row = voice_note_row(at, "Jane", "telegram", "Please call me", &path);
+        assert_eq!(
+            row,
+            format!(
+                "| 2026-10-15 09:30 | Jane | telegram | Voice note \"Please call me\" ({path}) |  |\n"
+            )
+        );
+    }
+
+    #[test]
+    fn inbound_email_document_and_log_row() {
+        let received_at = Utc.with_ymd_and_hms(2026, 10, 15, 9, 30, 0).unwrap();
+        let email = InboundEmail {
+            from: "Jane Client <jane@client.example>".to_string(),
+            to: vec!["intake+2024-0012@firm.example".to_string()],
+            cc: Vec::new(),
+            subject: "Signed | lease [Matter: 2024-0012]".to_string(),
+            body: "  Attached is the signed lease.  ".to_string(),
+            received_at,
+            message_id: Some("<abc@client.example>".to_string()),
+        };
+        let path = inbox_path("matters", "2024-0012", received_at, &email.subject);
+        assert_eq!(
+            path,
+            "matters/2024-0012/communications/inbox/2026-10-15-093000-signed-lease-matter-2024-0012.md"
+        );
+
+        let doc = render_inbound_email(
+            &email,
+            Some("2024-0012"),
+            &["matters/2024-0012/contracts/lease.txt".to_string()],
+            &["scan.pdf (not plain text)".to_string()],
+        );
+        assert!(
+            doc.contains(
+                "- **Matter:** 2024-0012\n- **From:** Jane Client <jane@client.example>\n"
+            )
+        );
+        assert!(doc.contains("- **Attachment:** matters/2024-0012/contracts/lease.txt\n"));
+        assert!(doc.contains("- **Attachment not filed:** scan.pdf (not plain text)\n"));
+        assert!(doc.ends_with("---\n\nAttached is the signed lease.\n"));
+
+        assert_eq!(
+            inbound_email_row(&email, &path),
+            format!(
+                "| 2026-10-15 09:30 | Jane Client <jane@client.example> | Email | Received \"Signed \\| lease [Matter: 2024-0012]\" ({path}) |  |\n"
+            )
+        );
+    }
+
+    #[test]
+    fn address_checks() {
+        assert_eq!(
+            parse_addresses(" a@x.com; b@y.org ,, "),
+            vec!["a@x.com", "b@y.org"]
+        );
+        assert!(is_plausible_address("a@x.com"));
+        for bad in ["a", "@x.com", "a@x", "a@.com", "a b@x.com"] {
+            assert!(!is_plausible_address(bad), "{bad}");
+        }
+    }
+}
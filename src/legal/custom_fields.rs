@@ -0,0 +1,513 @@
+//! Matter custom field schemas: typed, per-practice-area definitions for the
+//! free-form `MatterRecord.custom_fields` object.
+//!
+//! A firm defines schemas in the `matter_custom_fields` setting (managed at
+//! `/api/settings/custom-fields`). Each schema lists the practice areas it
+//! applies to (none means every matter) and its fields: a stable snake_case
+//! key, a label, a type, whether it is required, choice options, and whether
+//! global search indexes it. Matter create and update validate values
+//! against the fields that apply and store them normalized (numbers as JSON
+//! numbers, dates as `YYYY-MM-DD`, choices in their canonical spelling).
+//! Keys no schema defines stay free-form, so caption data such as `court`
+//! and `case_number` keep working. Templates read schema fields as
+//! `{{ matter.custom.<key> }}`.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::db::Database;
+
+/// Setting key holding the firm's schema list.
+pub const CUSTOM_FIELDS_SETTING_KEY: &str = "matter_custom_fields";
+
+const MAX_KEY_LEN: usize = 64;
+const MAX_TEXT_CHARS: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomFieldType {
+    Text,
+    Number,
+    /// `YYYY-MM-DD`; `MM/DD/YYYY` and RFC-3339 input is accepted.
+    Date,
+    Boolean,
+    /// One of `options`.
+    Choice,
+    /// Any subset of `options`, stored as an array.
+    MultiChoice,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomFieldDefinition {
+    pub key: String,
+    pub label: String,
+    #[serde(rename = "type")]
+    pub field_type: CustomFieldType,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    /// Include the value in global search results for the matter.
+    #[serde(default)]
+    pub searchable: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomFieldSchema {
+    pub id: String,
+    pub label: String,
+    /// Practice areas the schema applies to; empty applies to every matter.
+    #[serde(default)]
+    pub practice_areas: Vec<String>,
+    pub fields: Vec<CustomFieldDefinition>,
+}
+
+fn validate_key(kind: &str, key: &str) -> Result<(), String> {
+    if key.is_empty()
+        || key.len() > MAX_KEY_LEN
+        || !key.starts_with(|c: char| c.is_ascii_lowercase())
+        || !key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        return Err(format!(
+            "{kind} '{key}' must be 1-{MAX_KEY_LEN} lowercase letters, digits or '_', starting with a letter"
+        ));
+    }
+    Ok(())
+}
+
+fn validate_schemas(schemas: &[CustomFieldSchema]) -> Result<(), String> {
+    let mut schema_ids = HashSet::new();
+    for schema in schemas {
+        validate_key("schema id", &schema.id)?;
+        if !schema_ids.insert(schema.id.as_str()) {
+            return Err(format!("duplicate schema id '{}'", schema.id));
+        }
+        if schema.label.trim().is_empty() {
+            return Err(format!("schema '{}' needs a label", schema.id));
+        }
+        let mut keys = HashSet::new();
+        for field in &schema.fields {
+            validate_key("field key", &field.key)?;
+            if !keys.insert(field.key.as_str()) {
+                return Err(format!(
+                    "duplicate field key '{}' in schema '{}'",
+                    field.key, schema.id
+                ));
+            }
+            if field.label.trim().is_empty() {
+                return Err(format!("field '{}' needs a label", field.key));
+            }
+            let has_options = matches!(
+                field.field_type,
+                CustomFieldType::Choice | CustomFieldType::MultiChoice
+            );
+            if has_options && field.options.iter().all(|option| option.trim().is_empty()) {
+                return Err(format!("choice field '{}' has no options", field.key));
+            }
+            if !has_options && !field.options.is_empty() {
+                return Err(format!(
+                    "field '{}' lists options but is not a choice field",
+                    field.key
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parse and validate a `matter_custom_fields` setting value.
+pub fn parse_setting_value(value: &serde_json::Value) -> Result<Vec<CustomFieldSchema>, String> {
+    let schemas: Vec<CustomFieldSchema> = serde_json::from_value(value.clone())
+        .map_err(|e| format!("invalid custom field schemas: {e}"))?;
+    validate_schemas(&schemas)?;
+    Ok(schemas)
+}
+
+/// The firm's schemas from settings; none when the setting is absent or
+/// unreadable.
+pub async fn resolve_schemas(
+    store: Option<&Arc<dyn Database>>,
+    user_id: &str,
+) -> Vec<CustomFieldSchema> {
+    let Some(store) = store else {
+        return Vec::new();
+    };
+    match store.get_setting(user_id, CUSTOM_FIELDS_SETTING_KEY).await {
+        Ok(Some(value)) => match parse_setting_value(&value) {
+            Ok(schemas) => schemas,
+            Err(err) => {
+                tracing::warn!(
+                    user_id,
+                    "Ignoring invalid {CUSTOM_FIELDS_SETTING_KEY} setting: {err}"
+                );
+                Vec::new()
+            }
+        },
+        Ok(None) => Vec::new(),
+        Err(err) => {
+            tracing::warn!(
+                user_id,
+                "Failed to read {CUSTOM_FIELDS_SETTING_KEY} setting: {err}"
+            );
+            Vec::new()
+        }
+    }
+}
+
+/// Fields that apply to a matter: those of schemas matching its practice
+/// area first, then those of schemas for every matter. When two schemas
+/// define the same key, the first definition wins.
+pub fn fields_for_practice_area<'a>(
+    schemas: &'a [CustomFieldSchema],
+    practice_area: Option<&str>,
+) -> Vec<&'a CustomFieldDefinition> {
+    let specific = schemas.iter().filter(|schema| {
+        !schema.practice_areas.is_empty()
+            && practice_area.is_some_and(|area| {
+                crate::legal::scaffold::practice_area_matches(area, &schema.practice_areas)
+            })
+    });
+    let general = schemas
+        .iter()
+        .filter(|schema| schema.practice_areas.is_empty());
+    let mut seen = HashSet::new();
+    specific
+        .chain(general)
+        .flat_map(|schema| schema.fields.iter())
+        .filter(|field| seen.insert(field.key.as_str()))
+        .collect()
+}
+
+fn parse_date(raw: &str) -> Option<NaiveDate> {
+    let raw = raw.trim();
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(raw, "%m/%d/%Y"))
+        .ok()
+        .or_else(|| {
+            chrono::DateTime::parse_from_rfc3339(raw)
+                .ok()
+                .map(|dt| dt.date_naive())
+        })
+}
+
+fn canonical_option<'a>(field: &'a CustomFieldDefinition, raw: &str) -> Option<&'a String> {
+    let raw = raw.trim();
+    field
+        .options
+        .iter()
+        .find(|option| option.trim().eq_ignore_ascii_case(raw))
+}
+
+/// Normalize one value. `Ok(None)` means blank (treated as missing).
+fn normalize_value(
+    field: &CustomFieldDefinition,
+    value: &serde_json::Value,
+) -> Result<Option<serde_json::Value>, String> {
+    use serde_json::Value;
+
+    if value.is_null() || value.as_str().is_some_and(|raw| raw.trim().is_empty()) {
+        return Ok(None);
+    }
+    let invalid = |expected: &str| format!("'{}' must be {expected}", field.label);
+    let normalized = match field.field_type {
+        CustomFieldType::Text => {
+            let text = match value {
+                Value::String(raw) => raw.trim().to_string(),
+                Value::Number(number) => number.to_string(),
+                _ => return Err(invalid("text")),
+            };
+            if text.chars().count() > MAX_TEXT_CHARS {
+                return Err(format!(
+                    "'{}' must be at most {MAX_TEXT_CHARS} characters",
+                    field.label
+                ));
+            }
+            Value::String(text)
+        }
+        CustomFieldType::Number => match value {
+            Value::Number(_) => value.clone(),
+            Value::String(raw) => {
+                let cleaned = raw.trim().replace(',', "");
+                if let Ok(int) = cleaned.parse::<i64>() {
+                    Value::from(int)
+                } else {
+                    cleaned
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(serde_json::Number::from_f64)
+                        .map(Value::Number)
+                        .ok_or_else(|| invalid("a number"))?
+                }
+            }
+            _ => return Err(invalid("a number")),
+        },
+        CustomFieldType::Date => value
+            .as_str()
+            .and_then(parse_date)
+            .map(|date| Value::String(date.to_string()))
+            .ok_or_else(|| invalid("a date (YYYY-MM-DD)"))?,
+        CustomFieldType::Boolean => match value {
+            Value::Bool(_) => value.clone(),
+            Value::String(raw) => match raw.trim().to_ascii_lowercase().as_str() {
+                "true" | "yes" | "y" => Value::Bool(true),
+                "false" | "no" | "n" => Value::Bool(false),
+                _ => return Err(invalid("true or false")),
+            },
+            _ => return Err(invalid("true or false")),
+        },
+        CustomFieldType::Choice => value
+            .as_str()
+            .and_then(|raw| canonical_option(field, raw))
+            .map(|option| Value::String(option.clone()))
+            .ok_or_else(|| invalid(&format!("one of: {}", field.options.join(", "))))?,
+        CustomFieldType::MultiChoice => {
+            let items: Vec<&Value> = match value {
+                Value::Array(items) => items.iter().collect(),
+                Value::String(_) => vec![value],
+                _ => return Err(invalid("a list of options")),
+            };
+            let mut chosen: Vec<Value> = Vec::with_capacity(items.len());
+            for item in items {
+                let option = item
+                    .as_str()
+                    .and_then(|raw| canonical_option(field, raw))
+                    .ok_or_else(|| invalid(&format!("any of: {}", field.options.join(", "))))?;
+                let option = Value::String(option.clone());
+                if !chosen.contains(&option) {
+                    chosen.push(option);
+                }
+            }
+            if chosen.is_empty() {
+                return Ok(None);
+            }
+            Value::Array(chosen)
+        }
+    };
+    Ok(Some(normalized))
+}
+
+/// Validate `values` (a JSON object) against `fields` and return the
+/// normalized object. Blank schema fields are dropped; keys without a field
+/// pass through unchanged. Errors list every problem.
+pub fn validate_values(
+    fields: &[&CustomFieldDefinition],
+    values: &serde_json::Value,
+) -> Result<serde_json::Value, Vec<String>> {
+    let Some(map) = values.as_object() else {
+        return Err(vec!["custom fields must be a JSON object".to_string()]);
+    };
+    let mut out = map.clone();
+    let mut problems = Vec::new();
+    for field in fields {
+        let normalized = match map.get(&field.key) {
+            Some(value) => match normalize_value(field, value) {
+                Ok(normalized) => normalized,
+                Err(problem) => {
+                    problems.push(problem);
+                    continue;
+                }
+            },
+            None => None,
+        };
+        match normalized {
+            Some(value) => {
+                out.insert(field.key.clone(), value);
+            }
+            None => {
+                out.remove(&field.key);
+                if field.required {
+                    problems.push(format!("'{}' is required", field.label));
+                }
+            }
+        }
+    }
+    if problems.is_empty() {
+        Ok(serde_json::Value::Object(out))
+    } else {
+        Err(problems)
+    }
+}
+
+/// `matter.custom` for templates: every applicable field key, `null` when
+/// the matter has no value, so templates can test `{% if matter.custom.x %}`.
+pub fn template_values(
+    fields: &[&CustomFieldDefinition],
+    values: &serde_json::Value,
+) -> serde_json::Value {
+    let map = fields
+        .iter()
+        .map(|field| {
+            let value = values
+                .get(&field.key)
+                .cloned()
+                .unwrap_or(serde_json::Value::Null);
+            (field.key.clone(), value)
+        })
+        .collect();
+    serde_json::Value::Object(map)
+}
+
+/// `Label: value` lines for searchable fields that have a value.
+pub fn search_text(fields: &[&CustomFieldDefinition], values: &serde_json::Value) -> String {
+    fields
+        .iter()
+        .filter(|field| field.searchable)
+        .filter_map(|field| {
+            let value = match values.get(&field.key)? {
+                serde_json::Value::Null => return None,
+                serde_json::Value::String(text) => text.clone(),
+                serde_json::Value::Array(items) => items
+                    .iter()
+                    .filter_map(|item| item.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                other => other.to_string(),
+            };
+            Some(format!("{}: {value}", field.label))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schemas() -> Vec<CustomFieldSchema> {
+        parse_setting_value(&serde_json::json!([
+            {
+                "id": "general",
+                "label": "All matters",
+                "fields": [
+                    {"key": "referral_source", "label": "Referral source", "type": "text", "searchable": true},
+                    {"key": "injury_date", "label": "Generic date", "type": "date"}
+                ]
+            },
+            {
+                "id": "personal_injury",
+                "label": "Personal injury",
+                "practice_areas": ["personal injury"],
+                "fields": [
+                    {"key": "injury_date", "label": "Date of injury", "type": "date", "required": true},
+                    {"key": "policy_limit", "label": "Policy limit", "type": "number"},
+                    {"key": "liability", "label": "Liability", "type": "choice", "options": ["Clear", "Disputed"]},
+                    {"key": "treatment", "label": "Treatment", "type": "multi_choice", "options": ["ER", "PT", "Surgery"]},
+                    {"key": "litigated", "label": "In suit", "type": "boolean"}
+                ]
+            }
+        ]))
+        .expect("valid schemas")
+    }
+
+    #[test]
+    fn setting_validation_rejects_bad_keys_and_missing_options() {
+        assert!(
+            parse_setting_value(&serde_json::json!([
+                {"id": "x", "label": "X", "fields": [{"key": "Bad Key", "label": "B", "type": "text"}]}
+            ]))
+            .unwrap_err()
+            .contains("field key")
+        );
+        assert!(
+            parse_setting_value(&serde_json::json!([
+                {"id": "x", "label": "X", "fields": [{"key": "pick", "label": "Pick", "type": "choice"}]}
+            ]))
+            .unwrap_err()
+            .contains("no options")
+        );
+        assert!(
+            parse_setting_value(&serde_json::json!([
+                {"id": "x", "label": "X", "fields": []},
+                {"id": "x", "label": "Y", "fields": []}
+            ]))
+            .unwrap_err()
+            .contains("duplicate schema id")
+        );
+    }
+
+    #[test]
+    fn practice_area_fields_take_precedence_and_values_normalize() {
+        let schemas = schemas();
+        let pi = fields_for_practice_area(&schemas, Some("Personal Injury"));
+        let keys: Vec<&str> = pi.iter().map(|field| field.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "injury_date",
+                "policy_limit",
+                "liability",
+                "treatment",
+                "litigated",
+                "referral_source"
+            ]
+        );
+        assert_eq!(pi[0].label, "Date of injury");
+
+        let normalized = validate_values(
+            &pi,
+            &serde_json::json!({
+                "injury_date": "03/07/2025",
+                "policy_limit": "250,000",
+                "liability": "disputed",
+                "treatment": ["er", "Surgery", "ER"],
+                "litigated": "no",
+                "referral_source": "  ",
+                "court": "S.D.N.Y."
+            }),
+        )
+        .expect("valid values");
+        assert_eq!(
+            normalized,
+            serde_json::json!({
+                "injury_date": "2025-03-07",
+                "policy_limit": 250000,
+                "liability": "Disputed",
+                "treatment": ["ER", "Surgery"],
+                "litigated": false,
+                "court": "S.D.N.Y."
+            })
+        );
+
+        let problems = validate_values(
+            &pi,
+            &serde_json::json!({"policy_limit": "lots", "liability": "Maybe"}),
+        )
+        .unwrap_err();
+        assert_eq!(
+            problems,
+            vec![
+                "'Date of injury' is required".to_string(),
+                "'Policy limit' must be a number".to_string(),
+                "'Liability' must be one of: Clear, Disputed".to_string(),
+            ]
+        );
+
+        let general = fields_for_practice_area(&schemas, Some("Corporate"));
+        assert!(validate_values(&general, &serde_json::json!({})).is_ok());
+    }
+
+    #[test]
+    fn template_and_search_values_use_schema_keys() {
+        let schemas = schemas();
+        let fields = fields_for_practice_area(&schemas, Some("personal injury"));
+        let values = serde_json::json!({
+            "injury_date": "2025-03-07",
+            "treatment": ["ER", "PT"],
+            "referral_source": "Bar referral service"
+        });
+        let context = template_values(&fields, &values);
+        assert_eq!(context["injury_date"], "2025-03-07");
+        assert!(context["policy_limit"].is_null());
+        assert_eq!(
+            search_text(&fields, &values),
+            "Referral source: Bar referral service"
+        );
+    }
+}
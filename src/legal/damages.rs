@@ -0,0 +1,849 @@
+//! Damages models: special and general line items, pre- and post-judgment
+//! interest, and present-value discounting of future losses.
+//!
+//! A matter's model is stored as `damages/damages_model.json`; every change
+//! regenerates the `damages/damages_summary.md` table. Interest is simple
+//! interest on an actual/365 basis at the model's rates, falling back to the
+//! jurisdiction table in `interest_rates.toml`. Future losses (items due in
+//! some years, or paid annually) are discounted at the model's discount rate
+//! and earn no prejudgment interest.
+
+use std::sync::LazyLock;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::{Decimal, MathematicalOps};
+use serde::{Deserialize, Serialize};
+
+use crate::legal::markdown::table_cell;
+
+/// Stored model, relative to the matter folder.
+pub const DAMAGES_MODEL_FILE: &str = "damages/damages_model.json";
+/// Generated summary table, relative to the matter folder.
+pub const DAMAGES_SUMMARY_FILE: &str = "damages/damages_summary.md";
+
+const MAX_RATE_PERCENT: i64 = 100;
+const MAX_YEARS: u32 = 100;
+
+pub fn damages_model_path(matter_prefix: &str) -> String {
+    format!("{matter_prefix}/{DAMAGES_MODEL_FILE}")
+}
+
+pub fn damages_summary_path(matter_prefix: &str) -> String {
+    format!("{matter_prefix}/{DAMAGES_SUMMARY_FILE}")
+}
+
+/// Interest rates for one jurisdiction, as annual percentages.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct InterestRateProfile {
+    pub id: String,
+    pub label: String,
+    #[serde(default)]
+    pub codes: Vec<String>,
+    #[serde(default)]
+    pub prejudgment_rate: Option<f64>,
+    #[serde(default)]
+    pub general_prejudgment_rate: Option<f64>,
+    #[serde(default)]
+    pub postjudgment_rate: Option<f64>,
+    #[serde(default)]
+    pub authority: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InterestRatesConfig {
+    jurisdictions: Vec<InterestRateProfile>,
+}
+
+static INTEREST_RATES: LazyLock<Result<Vec<InterestRateProfile>, String>> =
+    LazyLock::new(|| parse_rate_profiles(include_str!("interest_rates.toml")));
+
+fn parse_rate_profiles(raw: &str) -> Result<Vec<InterestRateProfile>, String> {
+    let parsed: InterestRatesConfig =
+        toml::from_str(raw).map_err(|e| format!("invalid interest rates TOML: {}", e))?;
+    for profile in &parsed.jurisdictions {
+        let rates = [
+            profile.prejudgment_rate,
+            profile.general_prejudgment_rate,
+            profile.postjudgment_rate,
+        ];
+        if rates
+            .iter()
+            .flatten()
+            .any(|rate| !(0.0..=MAX_RATE_PERCENT as f64).contains(rate))
+        {
+            return Err(format!(
+                "interest rate profile '{}' has a rate outside 0-{MAX_RATE_PERCENT}%",
+                profile.id
+            ));
+        }
+    }
+    Ok(parsed.jurisdictions)
+}
+
+pub fn interest_rate_profiles() -> Result<&'static [InterestRateProfile], String> {
+    match &*INTEREST_RATES {
+        Ok(profiles) => Ok(profiles.as_slice()),
+        Err(err) => Err(err.clone()),
+    }
+}
+
+/// Rate profile listing the jurisdiction code, if any.
+pub fn rate_profile_for_jurisdiction(
+    jurisdiction: Option<&str>,
+) -> Result<Option<&'static InterestRateProfile>, String> {
+    let code: String = jurisdiction
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect();
+    if code.is_empty() {
+        return Ok(None);
+    }
+    Ok(interest_rate_profiles()?.iter().find(|profile| {
+        profile
+            .codes
+            .iter()
+            .any(|candidate| candidate.eq_ignore_ascii_case(&code))
+    }))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DamagesKind {
+    /// Pecuniary losses: medical expenses, lost income, repair costs.
+    Special,
+    /// Non-pecuniary losses: pain and suffering, loss of amenities.
+    General,
+}
+
+impl DamagesKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Special => "special",
+            Self::General => "general",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "special" | "pecuniary" => Some(Self::Special),
+            "general" | "non_pecuniary" | "non-pecuniary" => Some(Self::General),
+            _ => None,
+        }
+    }
+
+    fn id_prefix(self) -> &'static str {
+        match self {
+            Self::Special => "S",
+            Self::General => "G",
+        }
+    }
+
+    fn heading(self) -> &'static str {
+        match self {
+            Self::Special => "Special Damages",
+            Self::General => "General Damages",
+        }
+    }
+}
+
+/// One line of a damages model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DamagesItem {
+    /// `S1`, `S2`, ... for special damages; `G1`, ... for general damages.
+    pub id: String,
+    pub kind: DamagesKind,
+    pub description: String,
+    /// Lump sum, or the yearly amount when `annual_years` is set.
+    pub amount: Decimal,
+    /// When the loss was incurred; prejudgment interest runs from here.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+    /// Future loss payable this many years after the valuation date.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub years_until_due: Option<Decimal>,
+    /// Future loss recurring yearly for this many years.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annual_years: Option<u32>,
+    /// Receipt, invoice, or report supporting the amount.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl DamagesItem {
+    pub fn is_future(&self) -> bool {
+        self.years_until_due.is_some() || self.annual_years.is_some()
+    }
+
+    /// Undiscounted total; `None` when it overflows.
+    pub fn nominal(&self) -> Option<Decimal> {
+        self.amount
+            .checked_mul(Decimal::from(self.annual_years.unwrap_or(1)))
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.description.trim().is_empty() {
+            return Err("description is required".to_string());
+        }
+        if self.amount.is_sign_negative() {
+            return Err("amount must not be negative".to_string());
+        }
+        if let Some(years) = self.years_until_due
+            && (years.is_sign_negative() || years > Decimal::from(MAX_YEARS))
+        {
+            return Err(format!("years_until_due must be between 0 and {MAX_YEARS}"));
+        }
+        if let Some(years) = self.annual_years
+            && !(1..=MAX_YEARS).contains(&years)
+        {
+            return Err(format!("annual_years must be between 1 and {MAX_YEARS}"));
+        }
+        if self.nominal().is_none() {
+            return Err("amount x annual_years is too large".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// A matter's damages model. Rates are annual percentages and override the
+/// jurisdiction table when set.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DamagesModel {
+    #[serde(default)]
+    pub jurisdiction: Option<String>,
+    /// Cause-of-action date; prejudgment interest start for undated items.
+    #[serde(default)]
+    pub accrual_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub judgment_date: Option<NaiveDate>,
+    /// Date the model is valued at; today when unset.
+    #[serde(default)]
+    pub valuation_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub prejudgment_rate: Option<Decimal>,
+    #[serde(default)]
+    pub general_prejudgment_rate: Option<Decimal>,
+    #[serde(default)]
+    pub postjudgment_rate: Option<Decimal>,
+    #[serde(default)]
+    pub discount_rate: Option<Decimal>,
+    #[serde(default)]
+    pub items: Vec<DamagesItem>,
+}
+
+/// Check a rate is a percentage between 0 and 100.
+pub fn validate_rate(name: &str, rate: Decimal) -> Result<(), String> {
+    if rate.is_sign_negative() || rate > Decimal::from(MAX_RATE_PERCENT) {
+        return Err(format!(
+            "{name} must be a percentage between 0 and {MAX_RATE_PERCENT}"
+        ));
+    }
+    Ok(())
+}
+
+impl DamagesModel {
+    fn next_item_id(&self, kind: DamagesKind) -> String {
+        let prefix = kind.id_prefix();
+        let next = self
+            .items
+            .iter()
+            .filter_map(|item| item.id.strip_prefix(prefix)?.parse::<u32>().ok())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        format!("{prefix}{next}")
+    }
+
+    /// Add a line item, assigning it the next id for its kind.
+    pub fn add_item(&mut self, mut item: DamagesItem) -> Result<&DamagesItem, String> {
+        item.validate()?;
+        item.id = self.next_item_id(item.kind);
+        self.items.push(item);
+        Ok(self.items.last().expect("item just pushed"))
+    }
+
+    /// Remove a line item; ids of the remaining items do not change.
+    pub fn remove_item(&mut self, id: &str) -> Option<DamagesItem> {
+        let index = self
+            .items
+            .iter()
+            .position(|item| item.id.eq_ignore_ascii_case(id.trim()))?;
+        Some(self.items.remove(index))
+    }
+}
+
+/// Rates a calculation used, after applying the jurisdiction fallback.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AppliedRates {
+    pub prejudgment: Option<Decimal>,
+    pub general_prejudgment: Option<Decimal>,
+    pub postjudgment: Option<Decimal>,
+    pub discount: Option<Decimal>,
+    /// Jurisdiction rate table entry consulted, if any.
+    pub profile: Option<String>,
+    pub authority: Option<String>,
+}
+
+fn profile_rate(rate: Option<f64>) -> Option<Decimal> {
+    rate.and_then(Decimal::from_f64_retain)
+        .map(|rate| rate.round_dp(4))
+}
+
+fn applied_rates(model: &DamagesModel, profile: Option<&InterestRateProfile>) -> AppliedRates {
+    let prejudgment = model
+        .prejudgment_rate
+        .or_else(|| profile.and_then(|p| profile_rate(p.prejudgment_rate)));
+    AppliedRates {
+        prejudgment,
+        general_prejudgment: model
+            .general_prejudgment_rate
+            .or_else(|| profile.and_then(|p| profile_rate(p.general_prejudgment_rate)))
+            .or(prejudgment),
+        postjudgment: model
+            .postjudgment_rate
+            .or_else(|| profile.and_then(|p| profile_rate(p.postjudgment_rate))),
+        discount: model.discount_rate,
+        profile: profile.map(|p| p.label.clone()),
+        authority: profile.and_then(|p| p.authority.clone()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DamagesItemResult {
+    pub id: String,
+    pub kind: DamagesKind,
+    pub description: String,
+    pub nominal: Decimal,
+    pub present_value: Decimal,
+    pub interest_from: Option<NaiveDate>,
+    pub interest_days: i64,
+    pub interest_rate: Option<Decimal>,
+    pub prejudgment_interest: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DamagesCalculation {
+    pub valuation_date: NaiveDate,
+    /// Prejudgment interest end: the judgment date, else the valuation date.
+    pub interest_to: NaiveDate,
+    pub rates: AppliedRates,
+    pub items: Vec<DamagesItemResult>,
+    pub special_damages: Decimal,
+    pub general_damages: Decimal,
+    pub total_damages: Decimal,
+    pub prejudgment_interest: Decimal,
+    /// Damages plus prejudgment interest.
+    pub judgment_amount: Decimal,
+    pub postjudgment_days: i64,
+    pub postjudgment_interest: Decimal,
+    pub total_with_interest: Decimal,
+    pub warnings: Vec<String>,
+}
+
+fn checked_sum(values: impl IntoIterator<Item = Decimal>) -> Option<Decimal> {
+    values
+        .into_iter()
+        .try_fold(Decimal::ZERO, Decimal::checked_add)
+}
+
+fn too_large(what: &str) -> String {
+    format!("{what} is too large to calculate; check the amounts, rates, and years")
+}
+
+/// `principal * rate% * days / 365`; `None` on overflow.
+fn simple_interest(principal: Decimal, rate_percent: Decimal, days: i64) -> Option<Decimal> {
+    if days <= 0 {
+        return Some(Decimal::ZERO);
+    }
+    let yearly = principal.checked_mul(rate_percent)? / Decimal::ONE_HUNDRED;
+    Some(yearly.checked_mul(Decimal::from(days))? / Decimal::from(365))
+}
+
+/// `amount / (1 + rate%)^years`; `None` on overflow.
+fn discounted(amount: Decimal, rate_percent: Decimal, years: Decimal) -> Option<Decimal> {
+    if rate_percent.is_zero() || years.is_zero() {
+        return Some(amount);
+    }
+    let base = Decimal::ONE + rate_percent / Decimal::ONE_HUNDRED;
+    amount.checked_div(base.checked_powd(years)?)
+}
+
+fn present_value(item: &DamagesItem, discount: Decimal) -> Option<Decimal> {
+    let offset = item.years_until_due.unwrap_or_default();
+    match item.annual_years {
+        // Paid at the end of each year after the offset.
+        Some(years) => (1..=years).try_fold(Decimal::ZERO, |total, year| {
+            total.checked_add(discounted(
+                item.amount,
+                discount,
+                offset + Decimal::from(year),
+            )?)
+        }),
+        None => discounted(item.amount, discount, offset),
+    }
+}
+
+/// Value a damages model as of `today` (or its valuation date). Fails when
+/// a figure overflows, e.g. discounting at 100% over 100 years.
+pub fn calculate_damages(
+    model: &DamagesModel,
+    profile: Option<&InterestRateProfile>,
+    today: NaiveDate,
+) -> Result<DamagesCalculation, String> {
+    let rates = applied_rates(model, profile);
+    let valuation_date = model.valuation_date.unwrap_or(today);
+    let interest_to = model.judgment_date.unwrap_or(valuation_date);
+    let mut warnings = Vec::new();
+    let mut items = Vec::new();
+
+    let has_future = model.items.iter().any(DamagesItem::is_future);
+    if has_future && rates.discount.is_none() {
+        warnings
+            .push("No discount rate set; future losses are shown at nominal value.".to_string());
+    }
+    let mut missing_rate = false;
+    for item in &model.items {
+        let nominal = item
+            .nominal()
+            .ok_or_else(|| too_large(&format!("{} nominal value", item.id)))?
+            .round_dp(2);
+        let present_value = match (item.is_future(), rates.discount) {
+            (true, Some(discount)) => present_value(item, discount)
+                .ok_or_else(|| too_large(&format!("{} present value", item.id)))?
+                .round_dp(2),
+            _ => nominal,
+        };
+        let rate = match item.kind {
+            DamagesKind::Special => rates.prejudgment,
+            DamagesKind::General => rates.general_prejudgment,
+        };
+        let interest_from = if item.is_future() {
+            None
+        } else {
+            item.date.or(model.accrual_date)
+        };
+        let mut interest_days = 0;
+        let mut prejudgment_interest = Decimal::ZERO;
+        if !item.is_future() {
+            match (interest_from, rate) {
+                (None, _) => warnings.push(format!(
+                    "{}: no date or accrual date; no prejudgment interest computed.",
+                    item.id
+                )),
+                (Some(_), None) => missing_rate = true,
+                (Some(from), Some(rate)) => {
+                    interest_days = (interest_to - from).num_days().max(0);
+                    prejudgment_interest = simple_interest(present_value, rate, interest_days)
+                        .ok_or_else(|| too_large(&format!("{} prejudgment interest", item.id)))?
+                        .round_dp(2);
+                }
+            }
+        }
+        items.push(DamagesItemResult {
+            id: item.id.clone(),
+            kind: item.kind,
+            description: item.description.clone(),
+            nominal,
+            present_value,
+            interest_from,
+            interest_days,
+            interest_rate: if item.is_future() { None } else { rate },
+            prejudgment_interest,
+        });
+    }
+    if missing_rate {
+        warnings.push(match &rates.profile {
+            Some(profile) => format!(
+                "{profile} has no fixed prejudgment rate; set prejudgment_rate to the published rate."
+            ),
+            None => "No prejudgment rate for this jurisdiction; set prejudgment_rate.".to_string(),
+        });
+    }
+
+    let sum = |kind: Option<DamagesKind>,
+               pick: fn(&DamagesItemResult) -> Decimal|
+     -> Result<Decimal, String> {
+        checked_sum(
+            items
+                .iter()
+                .filter(|item| kind.is_none_or(|kind| item.kind == kind))
+                .map(pick),
+        )
+        .ok_or_else(|| too_large("the damages total"))
+    };
+    let special_damages = sum(Some(DamagesKind::Special), |item| item.present_value)?;
+    let general_damages = sum(Some(DamagesKind::General), |item| item.present_value)?;
+    let total_damages = special_damages
+        .checked_add(general_damages)
+        .ok_or_else(|| too_large("the damages total"))?;
+    let prejudgment_interest = sum(None, |item| item.prejudgment_interest)?;
+    let judgment_amount = total_damages
+        .checked_add(prejudgment_interest)
+        .ok_or_else(|| too_large("the judgment amount"))?;
+
+    let mut postjudgment_days = 0;
+    let mut postjudgment_interest = Decimal::ZERO;
+    if let Some(judgment_date) = model.judgment_date
+        && valuation_date > judgment_date
+    {
+        match rates.postjudgment {
+            Some(rate) => {
+                postjudgment_days = (valuation_date - judgment_date).num_days();
+                postjudgment_interest = simple_interest(judgment_amount, rate, postjudgment_days)
+                    .ok_or_else(|| too_large("post-judgment interest"))?
+                    .round_dp(2);
+            }
+            None => warnings.push(
+                "No post-judgment rate for this jurisdiction; set postjudgment_rate.".to_string(),
+            ),
+        }
+    }
+
+    let total_with_interest = judgment_amount
+        .checked_add(postjudgment_interest)
+        .ok_or_else(|| too_large("the total with interest"))?;
+    Ok(DamagesCalculation {
+        valuation_date,
+        interest_to,
+        rates,
+        items,
+        special_damages,
+        general_damages,
+        total_damages,
+        prejudgment_interest,
+        judgment_amount,
+        postjudgment_days,
+        postjudgment_interest,
+        total_with_interest,
+        warnings,
+    })
+}
+
+/// `$1,234.50`.
+pub fn format_money(amount: Decimal) -> String {
+    let formatted = format!("{:.2}", amount.abs().round_dp(2));
+    let (whole, fraction) = formatted
+        .split_once('.')
+        .unwrap_or((formatted.as_str(), "00"));
+    let mut grouped = String::new();
+    for (i, digit) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    let sign = if amount.is_sign_negative() && !amount.round_dp(2).is_zero() {
+        "-"
+    } else {
+        ""
+    };
+    format!("{sign}${grouped}.{fraction}")
+}
+
+fn percent(rate: Option<Decimal>) -> String {
+    rate.map(|rate| format!("{}%", rate.normalize()))
+        .unwrap_or_else(|| "not set".to_string())
+}
+
+/// Markdown summary table of a damages calculation.
+pub fn render_damages_summary(
+    matter_id: &str,
+    model: &DamagesModel,
+    calculation: &DamagesCalculation,
+    generated_at: DateTime<Utc>,
+) -> String {
+    let rates = &calculation.rates;
+    let mut out = format!(
+        "# Damages Summary: {matter_id}\n\nGenerated: {}\n\n",
+        generated_at.to_rfc3339()
+    );
+    out.push_str(&format!(
+        "- Jurisdiction: {}\n",
+        match (&model.jurisdiction, &rates.profile) {
+            (Some(code), Some(profile)) => format!("{code} ({profile})"),
+            (Some(code), None) => code.clone(),
+            (None, _) => "not set".to_string(),
+        }
+    ));
+    if let Some(authority) = &rates.authority {
+        out.push_str(&format!("- Rate authority: {authority}\n"));
+    }
+    out.push_str(&format!(
+        "- Valuation date: {}\n- Judgment date: {}\n- Prejudgment interest: {} ({} on general damages), to {}\n- Post-judgment interest: {}\n- Discount rate: {}\n\n",
+        calculation.valuation_date,
+        model
+            .judgment_date
+            .map(|date| date.to_string())
+            .unwrap_or_else(|| "not entered".to_string()),
+        percent(rates.prejudgment),
+        percent(rates.general_prejudgment),
+        calculation.interest_to,
+        percent(rates.postjudgment),
+        percent(rates.discount),
+    ));
+
+    for kind in [DamagesKind::Special, DamagesKind::General] {
+        out.push_str(&format!("## {}\n\n", kind.heading()));
+        let rows: Vec<(&DamagesItemResult, &DamagesItem)> = calculation
+            .items
+            .iter()
+            .zip(&model.items)
+            .filter(|(result, _)| result.kind == kind)
+            .collect();
+        if rows.is_empty() {
+            out.push_str("- None entered.\n\n");
+            continue;
+        }
+        out.push_str(
+            "| ID | Description | Date | Nominal | Present Value | Interest From | Days | Rate | Prejudgment Interest | Source |\n|---|---|---|---|---|---|---|---|---|---|\n",
+        );
+        for (result, item) in &rows {
+            let timing = match (item.years_until_due, item.annual_years) {
+                (_, Some(years)) => format!("{} x {years} yrs", format_money(item.amount)),
+                (Some(years), None) => format!("due in {} yrs", years.normalize()),
+                (None, None) => item.date.map(|d| d.to_string()).unwrap_or_default(),
+            };
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+                result.id,
+                table_cell(&result.description),
+                table_cell(&timing),
+                format_money(result.nominal),
+                format_money(result.present_value),
+                result
+                    .interest_from
+                    .map(|d| d.to_string())
+                    .unwrap_or_default(),
+                result.interest_days,
+                result
+                    .interest_rate
+                    .map(|rate| format!("{}%", rate.normalize()))
+                    .unwrap_or_default(),
+                format_money(result.prejudgment_interest),
+                table_cell(item.source.as_deref().unwrap_or_default()),
+            ));
+        }
+        // Both fit: calculate_damages already summed them checked.
+        let subtotal: Decimal = rows.iter().map(|(result, _)| result.present_value).sum();
+        let interest: Decimal = rows
+            .iter()
+            .map(|(result, _)| result.prejudgment_interest)
+            .sum();
+        out.push_str(&format!(
+            "|  | **Subtotal** |  |  | **{}** |  |  |  | **{}** |  |\n\n",
+            format_money(subtotal),
+            format_money(interest)
+        ));
+    }
+
+    out.push_str("## Totals\n\n| Component | Amount |\n|---|---|\n");
+    for (label, amount) in [
+        ("Special damages".to_string(), calculation.special_damages),
+        ("General damages".to_string(), calculation.general_damages),
+        ("Total damages".to_string(), calculation.total_damages),
+        (
+            "Prejudgment interest".to_string(),
+            calculation.prejudgment_interest,
+        ),
+        ("Judgment amount".to_string(), calculation.judgment_amount),
+        (
+            format!(
+                "Post-judgment interest ({} days)",
+                calculation.postjudgment_days
+            ),
+            calculation.postjudgment_interest,
+        ),
+    ] {
+        out.push_str(&format!("| {label} | {} |\n", format_money(amount)));
+    }
+    out.push_str(&format!(
+        "| **Total with interest** | **{}** |\n",
+        format_money(calculation.total_with_interest)
+    ));
+
+    if !calculation.warnings.is_empty() {
+        out.push_str("\n## Warnings\n\n");
+        for warning in &calculation.warnings {
+            out.push_str(&format!("- {warning}\n"));
+        }
+    }
+    out.push_str(
+        "\n_Simple interest, actual/365. Verify rates against the currently published rates before relying on these figures._\n",
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(raw: &str) -> Decimal {
+        raw.parse().unwrap()
+    }
+
+    fn date(raw: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(raw, "%Y-%m-%d").unwrap()
+    }
+
+    fn item(kind: DamagesKind, description: &str, amount: i64) -> DamagesItem {
+        DamagesItem {
+            id: String::new(),
+            kind,
+            description: description.to_string(),
+            amount: Decimal::from(amount),
+            date: None,
+            years_until_due: None,
+            annual_years: None,
+            source: None,
+        }
+    }
+
+    #[test]
+    fn bundled_rates_parse_and_match_jurisdiction_codes() {
+        assert!(!interest_rate_profiles().expect("rates").is_empty());
+        let ny = rate_profile_for_jurisdiction(Some("N.Y."))
+            .expect("rates")
+            .expect("ny profile");
+        assert_eq!(ny.prejudgment_rate, Some(9.0));
+        let ontario = rate_profile_for_jurisdiction(Some("on"))
+            .expect("rates")
+            .expect("ontario profile");
+        assert_eq!(ontario.prejudgment_rate, None);
+        assert_eq!(ontario.general_prejudgment_rate, Some(5.0));
+        assert!(rate_profile_for_jurisdiction(None).unwrap().is_none());
+    }
+
+    #[test]
+    fn items_get_stable_ids_per_kind() {
+        let mut model = DamagesModel::default();
+        for (kind, name) in [
+            (DamagesKind::Special, "MRI"),
+            (DamagesKind::General, "Pain and suffering"),
+            (DamagesKind::Special, "Lost wages"),
+        ] {
+            model.add_item(item(kind, name, 100)).expect("add");
+        }
+        let ids: Vec<&str> = model.items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["S1", "G1", "S2"]);
+        assert!(model.remove_item("s1").is_some());
+        model
+            .add_item(item(DamagesKind::Special, "Physio", 50))
+            .expect("add");
+        assert_eq!(model.items.last().unwrap().id, "S3");
+        assert!(model.add_item(item(DamagesKind::Special, " ", 1)).is_err());
+        assert!(
+            model
+                .add_item(item(DamagesKind::Special, "Refund", -5))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn interest_and_present_value_follow_jurisdiction_rates() {
+        let profile = rate_profile_for_jurisdiction(Some("NY")).unwrap();
+        let mut model = DamagesModel {
+            jurisdiction: Some("NY".to_string()),
+            accrual_date: Some(date("2024-01-01")),
+            judgment_date: Some(date("2025-01-01")),
+            valuation_date: Some(date("2025-07-02")),
+            discount_rate: Some(Decimal::from(5)),
+            ..DamagesModel::default()
+        };
+        let mut medical = item(DamagesKind::Special, "Hospital bills", 10_000);
+        medical.date = Some(date("2024-07-01"));
+        model.add_item(medical).unwrap();
+        model
+            .add_item(item(DamagesKind::General, "Pain and suffering", 50_000))
+            .unwrap();
+        let mut future = item(DamagesKind::Special, "Future surgery", 21_000);
+        future.years_until_due = Some(Decimal::ONE);
+        model.add_item(future).unwrap();
+        let mut wages = item(DamagesKind::Special, "Future lost income", 1_000);
+        wages.annual_years = Some(2);
+        model.add_item(wages).unwrap();
+
+        let calc = calculate_damages(&model, profile, date("2030-01-01")).expect("calculate");
+        assert_eq!(calc.valuation_date, date("2025-07-02"));
+        assert_eq!(calc.interest_to, date("2025-01-01"));
+        // 10,000 x 9% x 184/365
+        assert_eq!(calc.items[0].interest_days, 184);
+        assert_eq!(calc.items[0].prejudgment_interest, dec("453.70"));
+        // 50,000 x 9% x 366/365 (from the accrual date)
+        assert_eq!(calc.items[1].prejudgment_interest, dec("4512.33"));
+        // 21,000 / 1.05
+        assert_eq!(calc.items[2].present_value, dec("20000.00"));
+        assert_eq!(calc.items[2].prejudgment_interest, Decimal::ZERO);
+        // 1,000/1.05 + 1,000/1.05^2
+        assert_eq!(calc.items[3].present_value, dec("1859.41"));
+        assert_eq!(calc.special_damages, dec("31859.41"));
+        assert_eq!(calc.judgment_amount, dec("86825.44"));
+        assert_eq!(calc.postjudgment_days, 182);
+        assert_eq!(calc.postjudgment_interest, dec("3896.44"));
+        assert!(calc.warnings.is_empty(), "{:?}", calc.warnings);
+
+        let summary = render_damages_summary("demo", &model, &calc, Utc::now());
+        assert!(summary.contains("| S1 | Hospital bills | 2024-07-01 | $10,000.00 |"));
+        assert!(summary.contains("| **Total with interest** | **$90,721.88** |"));
+    }
+
+    #[test]
+    fn missing_rates_produce_warnings_instead_of_guesses() {
+        let profile = rate_profile_for_jurisdiction(Some("ON")).unwrap();
+        let mut model = DamagesModel {
+            jurisdiction: Some("ON".to_string()),
+            accrual_date: Some(date("2024-01-01")),
+            ..DamagesModel::default()
+        };
+        model
+            .add_item(item(DamagesKind::Special, "Lost wages", 1_000))
+            .unwrap();
+        model
+            .add_item(item(DamagesKind::General, "Non-pecuniary", 36_500))
+            .unwrap();
+        let mut future = item(DamagesKind::Special, "Future care", 5_000);
+        future.years_until_due = Some(Decimal::from(3));
+        model.add_item(future).unwrap();
+
+        let calc = calculate_damages(&model, profile, date("2025-01-01")).expect("calculate");
+        assert_eq!(calc.items[0].prejudgment_interest, Decimal::ZERO);
+        // r. 53.10: 5% on non-pecuniary damages, 366 days.
+        assert_eq!(calc.items[1].prejudgment_interest, dec("1830.00"));
+        assert_eq!(calc.items[2].present_value, dec("5000.00"));
+        assert_eq!(calc.warnings.len(), 2, "{:?}", calc.warnings);
+        assert_eq!(format_money(Decimal::new(123456789, 2)), "$1,234,567.89");
+    }
+
+    #[test]
+    fn figures_at_the_rate_and_year_bounds_fail_instead_of_overflowing() {
+        let mut model = DamagesModel {
+            discount_rate: Some(Decimal::from(MAX_RATE_PERCENT)),
+            ..DamagesModel::default()
+        };
+        let mut future = item(DamagesKind::Special, "Future care", 1_000_000);
+        future.years_until_due = Some(Decimal::from(MAX_YEARS));
+        model.add_item(future).unwrap();
+        let err = calculate_damages(&model, None, date("2025-01-01")).unwrap_err();
+        assert!(err.contains("S1 present value"), "{err}");
+
+        // Just inside the bounds still values: 1,000,000 / 2^90.
+        model.items[0].years_until_due = Some(Decimal::from(90));
+        let calc = calculate_damages(&model, None, date("2025-01-01")).expect("calculate");
+        assert_eq!(calc.items[0].present_value, Decimal::ZERO);
+
+        let mut annual = item(DamagesKind::Special, "Lost income", 0);
+        annual.amount = Decimal::MAX;
+        annual.annual_years = Some(MAX_YEARS);
+        assert!(model.add_item(annual).is_err());
+
+        let mut model = DamagesModel {
+            accrual_date: Some(date("1925-01-01")),
+            prejudgment_rate: Some(Decimal::from(MAX_RATE_PERCENT)),
+            ..DamagesModel::default()
+        };
+        let mut huge = item(DamagesKind::Special, "Judgment debt", 0);
+        huge.amount = Decimal::MAX / Decimal::TEN;
+        model.add_item(huge).unwrap();
+        let err = calculate_damages(&model, None, date("2025-01-01")).unwrap_err();
+        assert!(err.contains("S1 prejudgment interest"), "{err}");
+    }
+}
@@ -1,7 +1,244 @@
-use chrono::Utc;
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
 use tera::Context;
+use uuid::Uuid;
+
+use crate::db::{ClientRecord, DocumentTemplateRecord, MatterRecord};
+use crate::error::WorkspaceError;
+use crate::legal::caption::{CaptionParties, CaptionProfile};
+use crate::legal::locale::{Locale, template_variant_name};
+use crate::workspace::Workspace;
+
+/// Value type of a template variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateVariableType {
+    /// Single-line text.
+    #[default]
+    String,
+    /// Multi-line text.
+    Text,
+    Number,
+    Boolean,
+    /// `YYYY-MM-DD`.
+    Date,
+    /// One of `choices`.
+    Enum,
+    /// Array of values or objects (parties, claims), rendered with
+    /// `{% for %}` or the `join_list` filter.
+    List,
+}
+
+/// One entry of a template's `variables_json` schema. Values are supplied
+/// in the generation request's `extra` object and rendered as
+/// `{{ extra.<name> }}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateVariable {
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub var_type: TemplateVariableType,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Allowed values for `enum` variables.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<String>,
+}
+
+/// Parse a stored `variables_json` value. Plain strings in the array are
+/// older untyped entries and become optional string variables.
+pub fn parse_variable_schema(value: &serde_json::Value) -> Result<Vec<TemplateVariable>, String> {
+    let entries = match value {
+        serde_json::Value::Null => return Ok(Vec::new()),
+        serde_json::Value::Object(map) if map.is_empty() => return Ok(Vec::new()),
+        serde_json::Value::Array(entries) => entries,
+        _ => return Err("variables must be a JSON array".to_string()),
+    };
+    let mut schema: Vec<TemplateVariable> = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let variable = match entry {
+            serde_json::Value::String(name) => TemplateVariable {
+                name: name.clone(),
+                var_type: TemplateVariableType::String,
+                required: false,
+                description: None,
+                choices: Vec::new(),
+            },
+            other => serde_json::from_value::<TemplateVariable>(other.clone())
+                .map_err(|e| format!("invalid variable definition: {e}"))?,
+        };
+        let name = variable.name.as_str();
+        let valid_name = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_name {
+            return Err(format!(
+                "variable name '{name}' must start with a letter or underscore and contain only letters, digits, and underscores"
+            ));
+        }
+        if schema.iter().any(|existing| existing.name == name) {
+            return Err(format!("variable '{name}' is defined more than once"));
+        }
+        match variable.var_type {
+            TemplateVariableType::Enum if variable.choices.is_empty() => {
+                return Err(format!("enum variable '{name}' needs at least one choice"));
+            }
+            TemplateVariableType::Enum => {}
+            _ if !variable.choices.is_empty() => {
+                return Err(format!(
+                    "variable '{name}' has choices but is not of type enum"
+                ));
+            }
+            _ => {}
+        }
+        schema.push(variable);
+    }
+    Ok(schema)
+}
+
+/// Whether `value` counts as not supplied.
+fn is_blank(value: Option<&serde_json::Value>) -> bool {
+    match value {
+        None | Some(serde_json::Value::Null) => true,
+        Some(serde_json::Value::String(s)) => s.trim().is_empty(),
+        Some(serde_json::Value::Array(items)) => items.is_empty(),
+        Some(_) => false,
+    }
+}
+
+/// Required variables with no value in `extra`.
+pub fn missing_required_variables<'a>(
+    schema: &'a [TemplateVariable],
+    extra: &serde_json::Value,
+) -> Vec<&'a TemplateVariable> {
+    schema
+        .iter()
+        .filter(|variable| variable.required && is_blank(extra.get(&variable.name)))
+        .collect()
+}
+
+/// Check `extra` against `schema`, coercing string input for numbers,
+/// booleans, and dates. Returns the object to render with, or one message
+/// per missing or invalid variable. Keys not in the schema pass through.
+pub fn validate_variables(
+    schema: &[TemplateVariable],
+    extra: &serde_json::Value,
+) -> Result<serde_json::Value, Vec<String>> {
+    let mut values = extra.as_object().cloned().unwrap_or_default();
+    let mut problems = Vec::new();
+    for variable in schema {
+        let name = &variable.name;
+        let value = values.get(name);
+        if is_blank(value) {
+            if variable.required {
+                problems.push(format!("'{name}' is required"));
+            }
+            continue;
+        }
+        let Some(value) = value else { continue };
+        let coerced = match variable.var_type {
+            TemplateVariableType::String | TemplateVariableType::Text => value
+                .as_str()
+                .map(|s| serde_json::Value::String(s.to_string()))
+                .ok_or_else(|| format!("'{name}' must be text")),
+            TemplateVariableType::Number => match value {
+                serde_json::Value::Number(_) => Ok(value.clone()),
+                serde_json::Value::String(s) => s
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(serde_json::Number::from_f64)
+                    .map(serde_json::Value::Number)
+                    .ok_or_else(|| format!("'{name}' must be a number")),
+                _ => Err(format!("'{name}' must be a number")),
+            },
+            TemplateVariableType::Boolean => match value {
+                serde_json::Value::Bool(_) => Ok(value.clone()),
+                serde_json::Value::String(s) => match s.trim().to_ascii_lowercase().as_str() {
+                    "true" | "yes" => Ok(serde_json::Value::Bool(true)),
+                    "false" | "no" => Ok(serde_json::Value::Bool(false)),
+                    _ => Err(format!("'{name}' must be true or false")),
+                },
+                _ => Err(format!("'{name}' must be true or false")),
+            },
+            TemplateVariableType::Date => value
+                .as_str()
+                .and_then(|s| NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok())
+                .map(|date| serde_json::Value::String(date.to_string()))
+                .ok_or_else(|| format!("'{name}' must be a date (YYYY-MM-DD)")),
+            TemplateVariableType::Enum => value
+                .as_str()
+                .map(str::trim)
+                .filter(|s| variable.choices.iter().any(|choice| choice == s))
+                .map(|s| serde_json::Value::String(s.to_string()))
+                .ok_or_else(|| format!("'{name}' must be one of: {}", variable.choices.join(", "))),
+            // Interview answers arrive as text with one item per line.
+            TemplateVariableType::List => match value {
+                serde_json::Value::Array(_) => Ok(value.clone()),
+                serde_json::Value::String(s) => Ok(serde_json::Value::Array(
+                    s.lines()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty())
+                        .map(|line| serde_json::Value::String(line.to_string()))
+                        .collect(),
+                )),
+                _ => Err(format!("'{name}' must be a list")),
+            },
+        };
+        match coerced {
+            Ok(coerced) => {
+                values.insert(name.clone(), coerced);
+            }
+            Err(problem) => problems.push(problem),
+        }
+    }
+    if problems.is_empty() {
+        Ok(serde_json::Value::Object(values))
+    } else {
+        Err(problems)
+    }
+}
+
+/// Next free `drafts/` path for a document generated from `template_name`,
+/// or `None` if every candidate is taken.
+pub async fn generated_document_path(
+    workspace: &Workspace,
+    matter_prefix: &str,
+    template_name: &str,
+    timestamp: &str,
+) -> Result<Option<String>, WorkspaceError> {
+    let parsed = std::path::Path::new(template_name);
+    let stem = parsed
+        .file_stem()
+        .and_then(|value| value.to_str())
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or("generated-document");
+    let ext = parsed
+        .extension()
+        .and_then(|value| value.to_str())
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or("md");
 
-use crate::db::{ClientRecord, MatterRecord};
+    for counter in 1usize..=999 {
+        let suffix = if counter == 1 {
+            String::new()
+        } else {
+            format!("-{}", counter)
+        };
+        let candidate = format!("{matter_prefix}/drafts/{stem}-{timestamp}{suffix}.{ext}");
+        match workspace.read(&candidate).await {
+            Ok(_) => continue,
+            Err(WorkspaceError::DocumentNotFound { .. }) => return Ok(Some(candidate)),
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(None)
+}
 
 pub fn build_context(
     matter: &MatterRecord,
@@ -35,7 +272,115 @@ pub fn build_context(
     })
 }
 
+/// `context.caption`: the court profile and parties the caption filters use.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptionContext {
+    pub profile: CaptionProfile,
+    pub parties: CaptionParties,
+}
+
+/// Add the matter's caption data to a context from [`build_context`].
+pub fn insert_caption_context(context: &mut serde_json::Value, caption: &CaptionContext) {
+    if let (Some(map), Ok(value)) = (context.as_object_mut(), serde_json::to_value(caption)) {
+        map.insert("caption".to_string(), value);
+    }
+}
+
+/// Add `matter.custom` (schema-defined custom fields under their stable
+/// keys, see [`crate::legal::custom_fields::template_values`]) to a context
+/// from [`build_context`].
+pub fn insert_custom_field_context(context: &mut serde_json::Value, custom: serde_json::Value) {
+    if let Some(matter) = context
+        .get_mut("matter")
+        .and_then(serde_json::Value::as_object_mut)
+    {
+        matter.insert("custom".to_string(), custom);
+    }
+}
+
+/// Set `context.locale`, which the `currency`, `number`, and `legal_date`
+/// filters format for.
+pub fn insert_locale_context(context: &mut serde_json::Value, locale: Locale) {
+    if let Some(map) = context.as_object_mut() {
+        map.insert("locale".to_string(), serde_json::json!(locale.code()));
+    }
+}
+
+fn locale_from(context: &serde_json::Value) -> Locale {
+    context
+        .get("locale")
+        .and_then(|value| value.as_str())
+        .and_then(Locale::parse)
+        .unwrap_or_default()
+}
+
+/// The `locale` variant of `template` from `templates` (see
+/// [`template_variant_name`]), or `None` when there is none. Earlier entries
+/// win, so pass matter templates before shared ones.
+pub fn template_variant<'a>(
+    templates: &'a [DocumentTemplateRecord],
+    template: &DocumentTemplateRecord,
+    locale: Locale,
+) -> Option<&'a DocumentTemplateRecord> {
+    let name = template_variant_name(&template.name, locale);
+    if name == template.name {
+        return None;
+    }
+    templates.iter().find(|candidate| candidate.name == name)
+}
+
+/// Template bodies available to `{% include "<name>" %}`, keyed by name.
+pub type TemplatePartials = BTreeMap<String, String>;
+
+/// Name the rendered body is registered under; not a valid template name.
+const ROOT_TEMPLATE: &str = "__docgen_root__";
+
+/// Partials for rendering `rendering`: every other template in `templates`
+/// by name. Earlier entries win, so pass matter templates before shared ones.
+pub fn template_partials(
+    templates: &[DocumentTemplateRecord],
+    rendering: Uuid,
+) -> TemplatePartials {
+    let mut partials = TemplatePartials::new();
+    for template in templates.iter().filter(|t| t.id != rendering) {
+        partials
+            .entry(template.name.clone())
+            .or_insert_with(|| template.body.clone());
+    }
+    partials
+}
+
+/// Swap each partial for its `locale` variant where one exists, so
+/// `{% include "signature.md" %}` in a Spanish render uses `signature.es.md`.
+pub fn localize_partials(partials: &mut TemplatePartials, locale: Locale) {
+    let localized: Vec<(String, String)> = partials
+        .keys()
+        .filter_map(|name| {
+            let variant = template_variant_name(name, locale);
+            (variant != *name)
+                .then(|| partials.get(&variant))
+                .flatten()
+                .map(|body| (name.clone(), body.clone()))
+        })
+        .collect();
+    partials.extend(localized);
+}
+
 pub fn render_template(body: &str, context: &serde_json::Value) -> Result<String, String> {
+    render_template_with_partials(body, context, &TemplatePartials::new())
+}
+
+/// Render `body` with Tera: `{% if %}` / `{% for %}` blocks, the docgen
+/// filters (`currency`, `number`, and `legal_date` in the context's
+/// `locale`, `join_list`, and the caption filters
+/// `caption`, `signature_block`, `certificate_of_service`), and
+/// `{% include %}` of `partials`. Only partials reachable from `body` are parsed, and include
+/// cycles are rejected before rendering.
+pub fn render_template_with_partials(
+    body: &str,
+    context: &serde_json::Value,
+    partials: &TemplatePartials,
+) -> Result<String, String> {
     let map = context
         .as_object()
         .ok_or_else(|| "template context must be a JSON object at the root".to_string())?;
@@ -44,14 +389,343 @@ pub fn render_template(body: &str, context: &serde_json::Value) -> Result<String
         tera_context.insert(key, value);
     }
 
-    tera::Tera::one_off(body, &tera_context, false)
-        .map_err(|err| format!("failed to render template: {}", err))
+    let mut tera = tera::Tera::default();
+    tera.autoescape_on(Vec::new());
+    register_locale_filters(&mut tera, locale_from(context));
+    tera.register_filter("join_list", join_list_filter);
+    register_caption_filters(&mut tera, caption_context_from(context));
+    let mut sources = vec![(ROOT_TEMPLATE.to_string(), body.to_string())];
+    for name in reachable_partials(body, partials)? {
+        sources.push((name.clone(), partials[&name].clone()));
+    }
+    tera.add_raw_templates(sources)
+        .map_err(|err| format!("failed to parse template: {}", error_chain(&err)))?;
+    tera.render(ROOT_TEMPLATE, &tera_context)
+        .map_err(|err| format!("failed to render template: {}", error_chain(&err)))
+}
+
+/// Tera errors keep the useful detail in their source chain.
+fn error_chain(err: &tera::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = std::error::Error::source(err);
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+/// Names used in `{% include "..." %}` tags of `body`.
+fn included_names(body: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("{%") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("%}") else { break };
+        let tag = after[..end].trim_matches(|c: char| c == '-' || c.is_whitespace());
+        if let Some(args) = tag.strip_prefix("include") {
+            let args = args.trim_start();
+            if let Some(quote) = args.chars().next().filter(|c| *c == '"' || *c == '\'')
+                && let Some(name) = args[1..].split(quote).next()
+            {
+                names.push(name.to_string());
+            }
+        }
+        rest = &after[end + 2..];
+    }
+    names
+}
+
+/// Partials included from `body`, directly or through other partials.
+/// Unknown names are left for Tera to report when rendering.
+fn reachable_partials(body: &str, partials: &TemplatePartials) -> Result<Vec<String>, String> {
+    fn visit(
+        name: &str,
+        body: &str,
+        partials: &TemplatePartials,
+        path: &mut Vec<String>,
+        seen: &mut Vec<String>,
+    ) -> Result<(), String> {
+        for included in included_names(body) {
+            if included == name || path.contains(&included) {
+                path.push(included);
+                return Err(format!("template include cycle: {}", path.join(" -> ")));
+            }
+            let Some(partial) = partials.get(&included) else {
+                continue;
+            };
+            if !seen.contains(&included) {
+                seen.push(included.clone());
+            }
+            path.push(included.clone());
+            visit(&included, partial, partials, path, seen)?;
+            path.pop();
+        }
+        Ok(())
+    }
+
+    let mut seen = Vec::new();
+    visit(
+        ROOT_TEMPLATE,
+        body,
+        partials,
+        &mut vec!["(template)".to_string()],
+        &mut seen,
+    )?;
+    Ok(seen)
+}
+
+fn filter_number(filter: &str, value: &tera::Value) -> tera::Result<f64> {
+    match value {
+        tera::Value::Number(n) => n.as_f64(),
+        tera::Value::String(s) => s.trim().replace(',', "").parse::<f64>().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| tera::Error::msg(format!("`{filter}` expects a number, got {value}")))
+}
+
+/// Locale for a locale-aware filter: its `locale` argument, otherwise the
+/// context's.
+fn filter_locale(
+    filter: &str,
+    args: &HashMap<String, tera::Value>,
+    default: Locale,
+) -> tera::Result<Locale> {
+    match args.get("locale").and_then(|v| v.as_str()) {
+        None => Ok(default),
+        Some(raw) => Locale::parse(raw)
+            .ok_or_else(|| tera::Error::msg(format!("`{filter}` unknown locale '{raw}'"))),
+    }
+}
+
+fn filter_decimals(args: &HashMap<String, tera::Value>, default: u64) -> usize {
+    args.get("decimals")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(default)
+        .min(6) as usize
+}
+
+/// Register `currency`, `number`, and `legal_date`, formatting for
+/// `locale` unless a filter is given its own `locale` argument.
+///
+/// - `{{ amount | currency }}` -> `$1,234.50`, or `1.234,50 $` for `es`.
+///   Arguments: `symbol` (default `$`) and `decimals` (default 2).
+/// - `{{ count | number }}` -> `1,234` with the locale's separators.
+///   `decimals` defaults to 0 for whole numbers and 2 otherwise.
+/// - `{{ date | legal_date }}` for `YYYY-MM-DD` or RFC 3339 input. `style`:
+///   `long` (default, `March 2, 2026` / `2 de marzo de 2026`), `ordinal`
+///   (`2nd day of March, 2026`), `short` (`03/02/2026`), or `iso`.
+fn register_locale_filters(tera: &mut tera::Tera, locale: Locale) {
+    tera.register_filter(
+        "currency",
+        move |value: &tera::Value, args: &HashMap<String, tera::Value>| {
+            let amount = filter_number("currency", value)?;
+            let symbol = args.get("symbol").and_then(|v| v.as_str()).unwrap_or("$");
+            let locale = filter_locale("currency", args, locale)?;
+            Ok(tera::Value::String(locale.format_currency(
+                amount,
+                symbol,
+                filter_decimals(args, 2),
+            )))
+        },
+    );
+    tera.register_filter(
+        "number",
+        move |value: &tera::Value, args: &HashMap<String, tera::Value>| {
+            let number = filter_number("number", value)?;
+            let default_decimals = if number.fract() == 0.0 { 0 } else { 2 };
+            let locale = filter_locale("number", args, locale)?;
+            Ok(tera::Value::String(locale.format_number(
+                number,
+                filter_decimals(args, default_decimals),
+            )))
+        },
+    );
+    tera.register_filter(
+        "legal_date",
+        move |value: &tera::Value, args: &HashMap<String, tera::Value>| {
+            let raw = value
+                .as_str()
+                .ok_or_else(|| {
+                    tera::Error::msg(format!("`legal_date` expects a date, got {value}"))
+                })?
+                .trim();
+            let date = NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                .ok()
+                .or_else(|| {
+                    chrono::DateTime::parse_from_rfc3339(raw)
+                        .ok()
+                        .map(|dt| dt.date_naive())
+                })
+                .ok_or_else(|| {
+                    tera::Error::msg(format!("`legal_date` cannot parse date '{raw}'"))
+                })?;
+            let style = args.get("style").and_then(|v| v.as_str()).unwrap_or("long");
+            let locale = filter_locale("legal_date", args, locale)?;
+            locale
+                .format_date(date, style)
+                .map(tera::Value::String)
+                .map_err(|err| tera::Error::msg(format!("`legal_date` {err}")))
+        },
+    );
+}
+
+/// `{{ parties | join_list(key="name") }}` -> `A, B, and C`. Arguments:
+/// `key` to read from object items and `conjunction` (default `and`).
+fn join_list_filter(
+    value: &tera::Value,
+    args: &HashMap<String, tera::Value>,
+) -> tera::Result<tera::Value> {
+    let items = value
+        .as_array()
+        .ok_or_else(|| tera::Error::msg(format!("`join_list` expects a list, got {value}")))?;
+    let key = args.get("key").and_then(|v| v.as_str());
+    let conjunction = args
+        .get("conjunction")
+        .and_then(|v| v.as_str())
+        .unwrap_or("and");
+    let names: Vec<String> = items
+        .iter()
+        .map(|item| {
+            let item = match key {
+                Some(key) => item.get(key).unwrap_or(&tera::Value::Null),
+                None => item,
+            };
+            match item {
+                tera::Value::String(s) => s.clone(),
+                tera::Value::Null => String::new(),
+                other => other.to_string(),
+            }
+        })
+        .filter(|name| !name.trim().is_empty())
+        .collect();
+    Ok(tera::Value::String(join_names(&names, conjunction)))
+}
+
+/// `A`, `A and B`, or `A, B, and C`.
+pub fn join_names(names: &[String], conjunction: &str) -> String {
+    match names {
+        [] => String::new(),
+        [only] => only.clone(),
+        [first, second] => format!("{first} {conjunction} {second}"),
+        [rest @ .., last] => format!("{}, {conjunction} {last}", rest.join(", ")),
+    }
+}
+
+/// Caption data for the caption filters, from `context.caption` when the
+/// caller added it, otherwise from the matter's jurisdiction and client.
+fn caption_context_from(context: &serde_json::Value) -> CaptionContext {
+    if let Some(caption) = context
+        .get("caption")
+        .and_then(|value| serde_json::from_value::<CaptionContext>(value.clone()).ok())
+    {
+        return caption;
+    }
+    let jurisdiction = context
+        .pointer("/matter/jurisdiction")
+        .and_then(|value| value.as_str());
+    let client = context
+        .pointer("/client/name")
+        .and_then(|value| value.as_str());
+    CaptionContext {
+        profile: crate::legal::caption::caption_profile(jurisdiction, None),
+        parties: crate::legal::caption::CaptionParties {
+            plaintiffs: client.map(str::to_string).into_iter().collect(),
+            ..Default::default()
+        },
+    }
+}
+
+fn filter_date(filter: &str, args: &HashMap<String, tera::Value>) -> tera::Result<NaiveDate> {
+    match args.get("date").and_then(|value| value.as_str()) {
+        None => Ok(Utc::now().date_naive()),
+        Some(raw) => NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d").map_err(|_| {
+            tera::Error::msg(format!("`{filter}` date must be YYYY-MM-DD, got '{raw}'"))
+        }),
+    }
+}
+
+fn register_caption_filters(tera: &mut tera::Tera, caption: CaptionContext) {
+    let for_caption = caption.clone();
+    tera.register_filter(
+        "caption",
+        move |value: &tera::Value, _: &HashMap<String, tera::Value>| {
+            let title = value
+                .as_str()
+                .ok_or_else(|| tera::Error::msg("`caption` expects the document title"))?;
+            Ok(tera::Value::String(crate::legal::caption::render_caption(
+                &for_caption.profile,
+                &for_caption.parties,
+                title,
+            )))
+        },
+    );
+    let for_signature = caption.clone();
+    tera.register_filter(
+        "signature_block",
+        move |value: &tera::Value, args: &HashMap<String, tera::Value>| {
+            let signer = match value {
+                tera::Value::String(name) => crate::legal::caption::CaptionSigner {
+                    name: name.clone(),
+                    ..Default::default()
+                },
+                other => serde_json::from_value(other.clone()).map_err(|err| {
+                    tera::Error::msg(format!("`signature_block` expects an attorney: {err}"))
+                })?,
+            };
+            Ok(tera::Value::String(
+                crate::legal::caption::render_signature_block(
+                    &for_signature.profile,
+                    &signer,
+                    &for_signature.parties,
+                    filter_date("signature_block", args)?,
+                ),
+            ))
+        },
+    );
+    tera.register_filter(
+        "certificate_of_service",
+        move |value: &tera::Value, args: &HashMap<String, tera::Value>| {
+            let served: Vec<crate::legal::caption::ServiceEntry> = serde_json::from_value(
+                value.clone(),
+            )
+            .map_err(|err| {
+                tera::Error::msg(format!(
+                    "`certificate_of_service` expects a list of {{name, method, address}}: {err}"
+                ))
+            })?;
+            let arg = |name: &str| {
+                args.get(name)
+                    .and_then(|value| value.as_str())
+                    .map(str::to_string)
+                    .ok_or_else(|| {
+                        tera::Error::msg(format!("`certificate_of_service` needs `{name}`"))
+                    })
+            };
+            Ok(tera::Value::String(
+                crate::legal::caption::render_certificate_of_service(
+                    &caption.profile,
+                    &arg("title")?,
+                    &served,
+                    filter_date("certificate_of_service", args)?,
+                    &arg("signer")?,
+                ),
+            ))
+        },
+    );
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{build_context, render_template};
-    use crate::db::{ClientRecord, ClientType, MatterRecord, MatterStatus};
+    use super::{
+        TemplatePartials, TemplateVariableType, build_context, insert_caption_context,
+        insert_locale_context, localize_partials, missing_required_variables,
+        parse_variable_schema, render_template, render_template_with_partials, template_variant,
+        validate_variables,
+    };
+    use crate::db::{ClientRecord, ClientType, DocumentTemplateRecord, MatterRecord, MatterStatus};
+    use crate::legal::locale::Locale;
     use chrono::Utc;
     use uuid::Uuid;
 
@@ -105,4 +779,215 @@ mod tests {
         .expect("render should succeed");
         assert!(rendered.contains("Matter demo for Acme Corp (summary)"));
     }
+
+    #[test]
+    fn clauses_render_conditionally_with_loops_filters_and_partials() {
+        let client_id = Uuid::new_v4();
+        let context = build_context(
+            &sample_matter(client_id),
+            &sample_client(client_id),
+            Some(&serde_json::json!({
+                "parties": [{"name": "Acme Corp"}, {"name": "Globex"}, {"name": "Initech"}],
+                "claims": ["Breach of contract", "Unjust enrichment"],
+                "jury_demand": false,
+                "damages": 1234567.5,
+                "filed_on": "2026-03-02",
+            })),
+        );
+        let mut partials = TemplatePartials::new();
+        partials.insert(
+            "signature.md".to_string(),
+            "Dated: {{ extra.filed_on | legal_date(style=\"ordinal\") }}".to_string(),
+        );
+        let body = "Parties: {{ extra.parties | join_list(key=\"name\") }}\n\
+            {% for claim in extra.claims %}Count {{ loop.index }}: {{ claim }}\n{% endfor %}\
+            {% if extra.jury_demand %}JURY DEMANDED\n{% endif %}\
+            Damages: {{ extra.damages | currency }} on {{ extra.filed_on | legal_date }}\n\
+            {% include \"signature.md\" %}";
+
+        let rendered = render_template_with_partials(body, &context, &partials).expect("render");
+        assert_eq!(
+            rendered,
+            "Parties: Acme Corp, Globex, and Initech\n\
+             Count 1: Breach of contract\nCount 2: Unjust enrichment\n\
+             Damages: $1,234,567.50 on March 2, 2026\n\
+             Dated: 2nd day of March, 2026"
+        );
+
+        partials.insert("a.md".to_string(), "{% include \"b.md\" %}".to_string());
+        partials.insert("b.md".to_string(), "{% include \"a.md\" %}".to_string());
+        let err = render_template_with_partials("{% include \"a.md\" %}", &context, &partials)
+            .expect_err("include cycle");
+        assert!(err.contains("a.md -> b.md -> a.md"), "{err}");
+        let err =
+            render_template("{{ client.name | currency }}", &context).expect_err("not a number");
+        assert!(err.contains("`currency` expects a number"), "{err}");
+    }
+
+    #[test]
+    fn locale_selects_variants_and_formats_filters() {
+        let client_id = Uuid::new_v4();
+        let template = |name: &str, matter_id: Option<&str>, body: &str| DocumentTemplateRecord {
+            id: Uuid::new_v4(),
+            user_id: "default".to_string(),
+            matter_id: matter_id.map(str::to_string),
+            name: name.to_string(),
+            body: body.to_string(),
+            variables_json: serde_json::json!([]),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let templates = vec![
+            template("letter.es.md", Some("demo"), "matter"),
+            template("letter.md", None, "base"),
+            template("letter.es.md", None, "shared"),
+        ];
+        let variant = template_variant(&templates, &templates[1], Locale::Es).expect("variant");
+        assert_eq!(variant.body, "matter");
+        assert!(template_variant(&templates, &templates[1], Locale::Fr).is_none());
+        assert!(template_variant(&templates, &templates[1], Locale::EnUs).is_none());
+
+        let mut context = build_context(
+            &sample_matter(client_id),
+            &sample_client(client_id),
+            Some(&serde_json::json!({"fee": 12500.5, "hours": 1500, "due": "2026-03-02"})),
+        );
+        insert_locale_context(&mut context, Locale::Es);
+        let mut partials = TemplatePartials::new();
+        partials.insert("sign.md".to_string(), "Signed".to_string());
+        partials.insert("sign.es.md".to_string(), "Firmado".to_string());
+        localize_partials(&mut partials, Locale::Es);
+        let rendered = render_template_with_partials(
+            "{{ extra.fee | currency(symbol=\"€\") }} · {{ extra.hours | number }} · \
+             {{ extra.due | legal_date }} · {{ extra.due | legal_date(locale=\"en\") }} · \
+             {% include \"sign.md\" %}",
+            &context,
+            &partials,
+        )
+        .expect("render");
+        assert_eq!(
+            rendered,
+            "12.500,50\u{a0}€ · 1.500 · 2 de marzo de 2026 · March 2, 2026 · Firmado"
+        );
+        let err = render_template(
+            "{{ extra.due | legal_date(locale=\"xx-unknown\") }}",
+            &context,
+        )
+        .expect_err("unknown locale");
+        assert!(err.contains("unknown locale"), "{err}");
+    }
+
+    #[test]
+    fn caption_filters_use_the_matter_caption_context() {
+        let client_id = Uuid::new_v4();
+        let mut context = build_context(
+            &sample_matter(client_id),
+            &sample_client(client_id),
+            Some(&serde_json::json!({
+                "attorney": {"name": "Jane Doe", "bar_number": "JD1234"},
+                "served": [{"name": "Globex", "method": "CM/ECF"}],
+            })),
+        );
+        insert_caption_context(
+            &mut context,
+            &crate::legal::caption::matter_caption(
+                &sample_matter(client_id),
+                &sample_client(client_id),
+                &[],
+                None,
+            ),
+        );
+        let rendered = render_template(
+            "{{ \"Complaint\" | caption }}\n\n\
+             {{ extra.attorney | signature_block(date=\"2026-03-02\") }}\n\n\
+             {{ extra.served | certificate_of_service(title=\"Complaint\", signer=\"Jane Doe\", date=\"2026-03-02\") }}",
+            &context,
+        )
+        .expect("render");
+        assert!(
+            rendered.starts_with("UNITED STATES DISTRICT COURT\nSOUTHERN DISTRICT OF NEW YORK")
+        );
+        assert!(rendered.contains("Case No. 24-cv-100"));
+        assert!(rendered.contains("ACME CORP,\n        Plaintiff,"));
+        assert!(rendered.contains("/s/ Jane Doe\nJane Doe\nBar No. JD1234"));
+        assert!(rendered.contains("- Globex (by CM/ECF)"));
+    }
+
+    #[test]
+    fn variable_schema_parses_typed_and_legacy_entries() {
+        let schema = parse_variable_schema(&serde_json::json!([
+            "reference",
+            {"name": "court", "type": "enum", "required": true, "choices": ["SDNY", "EDNY"]},
+            {"name": "hearing_date", "type": "date", "description": "Date of the hearing"},
+        ]))
+        .expect("valid schema");
+        assert_eq!(schema.len(), 3);
+        assert_eq!(schema[0].var_type, TemplateVariableType::String);
+        assert!(!schema[0].required);
+        assert_eq!(schema[1].choices, vec!["SDNY", "EDNY"]);
+
+        assert!(
+            parse_variable_schema(&serde_json::json!({}))
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            parse_variable_schema(&serde_json::json!([{"name": "x", "type": "enum"}])).is_err()
+        );
+        assert!(parse_variable_schema(&serde_json::json!(["a", "a"])).is_err());
+        assert!(parse_variable_schema(&serde_json::json!(["bad name"])).is_err());
+        assert!(
+            parse_variable_schema(&serde_json::json!([{"name": "x", "choices": ["a"]}])).is_err()
+        );
+    }
+
+    #[test]
+    fn variables_are_validated_and_coerced() {
+        let schema = parse_variable_schema(&serde_json::json!([
+            {"name": "court", "type": "enum", "required": true, "choices": ["SDNY", "EDNY"]},
+            {"name": "amount", "type": "number", "required": true},
+            {"name": "expedited", "type": "boolean"},
+            {"name": "hearing_date", "type": "date"},
+            {"name": "parties", "type": "list"},
+        ]))
+        .unwrap();
+
+        let missing = missing_required_variables(&schema, &serde_json::json!({"court": " "}));
+        let names: Vec<_> = missing.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["court", "amount"]);
+
+        let values = validate_variables(
+            &schema,
+            &serde_json::json!({
+                "court": "SDNY",
+                "amount": "1500.5",
+                "expedited": "yes",
+                "hearing_date": "2026-03-02",
+                "parties": "Acme Corp\n\nGlobex\n",
+                "note": "passes through",
+            }),
+        )
+        .expect("valid values");
+        assert_eq!(values["amount"], 1500.5);
+        assert_eq!(
+            values["parties"],
+            serde_json::json!(["Acme Corp", "Globex"])
+        );
+        assert_eq!(values["expedited"], true);
+        assert_eq!(values["note"], "passes through");
+
+        let problems = validate_variables(
+            &schema,
+            &serde_json::json!({"court": "CDCA", "hearing_date": "March 2"}),
+        )
+        .expect_err("invalid values");
+        assert_eq!(
+            problems,
+            vec![
+                "'court' must be one of: SDNY, EDNY",
+                "'amount' is required",
+                "'hearing_date' must be a date (YYYY-MM-DD)",
+            ]
+        );
+    }
 }
@@ -0,0 +1,816 @@
+//! Rule-based checks for drafts.
+//!
+//! [`check_draft`] runs deterministic checks that complement LLM review:
+//!
+//! - `undefined_term`: a capitalized phrase used as a term ("the Closing
+//!   Date") that the draft never defines.
+//! - `defined_term`: a term defined twice, defined but never used, or used
+//!   in lower case ("the purchase price").
+//! - `party_name`: a party named in more than one form ("Acme Corp." and
+//!   "Acme Corporation"), or a party's defined name in lower case.
+//! - `cross_reference`: a reference to a section that does not exist ("see
+//!   Section 4.2").
+//! - `numbering`: section numbers that skip, repeat, or run backwards.
+//! - `double_space` and `repeated_word`: typing slips.
+//!
+//! Definitions are quoted capitalized phrases inside parentheses
+//! (`(the "Agreement")`) or followed by `means` / `shall have the meaning`.
+//! Fenced code blocks are skipped.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::legal::filing_validation::IssueSeverity;
+
+/// Check names accepted by [`check_draft`].
+pub const CHECKS: [&str; 7] = [
+    "undefined_term",
+    "defined_term",
+    "party_name",
+    "cross_reference",
+    "numbering",
+    "double_space",
+    "repeated_word",
+];
+
+/// Longest excerpt of the offending line kept on an issue.
+const MAX_EXCERPT_CHARS: usize = 160;
+
+/// Capitalized phrases that read as terms but need no definition.
+const COMMON_PROPER_TERMS: [&str; 10] = [
+    "Court",
+    "State",
+    "United States",
+    "Commonwealth",
+    "Internet",
+    "Effective Time",
+    "Federal Government",
+    "Bankruptcy Code",
+    "Internal Revenue Code",
+    "Securities Act",
+];
+
+/// Words that are often doubled on purpose ("had had", "that that").
+const ALLOWED_REPEATS: [&str; 2] = ["had", "that"];
+
+static QUOTED_TERM_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"["“]([A-Z][\w'’&-]*(?:\s+(?:of|and|for|the|to|[A-Z][\w'’&-]*))*)["”]"#)
+        .expect("valid quoted term regex")
+});
+
+static DEFINING_VERB_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*(?:\*\*|__)?\s*(?:,\s*)?(?:means|shall mean|has the meaning|shall have the meaning|refers to|is defined)\b",
+    )
+    .expect("valid defining verb regex")
+});
+
+static ENTITY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\b([A-Z][A-Za-z0-9&'’-]*(?:\s+[A-Z][A-Za-z0-9&'’-]*){0,3})(,?\s+)(Inc\.|Inc\b|L\.L\.C\.|LLC\b|Corp\.|Corp\b|Corporation\b|Company\b|Co\.|Ltd\.|Ltd\b|Limited\b|LLP\b|L\.P\.|LP\b)",
+    )
+    .expect("valid entity name regex")
+});
+
+static PARTY_CONTEXT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:inc\.?|llc|l\.l\.c\.|corp\.?|corporation|company|co\.|ltd\.?|limited|llp|l\.p\.|lp|partnership|individual|trust)(?:\W|$)")
+        .expect("valid party context regex")
+});
+
+static TERM_USE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?:the|The|such|each|any|said)\s+([A-Z][a-z][\w'’-]*(?:\s+(?:of\s+)?[A-Z][a-z][\w'’-]*){0,3})")
+        .expect("valid term use regex")
+});
+
+static SECTION_LINE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*(#{1,6}\s+)?(?:\*\*|__)?\s*((?:Section|SECTION|Clause|CLAUSE|Article|ARTICLE)\s+)?(\d{1,3}(?:\.\d{1,3})*)(\.|\)|\s|$)",
+    )
+    .expect("valid section line regex")
+});
+
+static SECTION_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\b(?:Sections?|Clauses?|Paragraphs?)\s+(\d+(?:\.\d+)*(?:\([A-Za-z0-9]+\))*(?:(?:\s*,\s*(?:and\s+|or\s+)?|\s+(?:and|or|through|to)\s+|\s*-\s*)\d+(?:\.\d+)*(?:\([A-Za-z0-9]+\))*)*)",
+    )
+    .expect("valid section reference regex")
+});
+
+static SECTION_NUMBER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\d+(?:\.\d+)*").expect("valid section number regex"));
+
+static EXTERNAL_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s+of\s+(?:the\s+)?(?:[A-Z0-9]|[a-z]+\s+[A-Z])")
+        .expect("valid external reference regex")
+});
+
+static DOUBLE_SPACE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\S {2,}\S").expect("valid double space regex"));
+
+static WORD_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[A-Za-z][A-Za-z'’]*").expect("valid word regex"));
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DraftIssue {
+    /// One of [`CHECKS`].
+    pub check: &'static str,
+    pub severity: IssueSeverity,
+    /// 1-based line of the first occurrence.
+    pub line: usize,
+    pub excerpt: String,
+    pub message: String,
+}
+
+/// A term the draft defines.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DefinedTerm {
+    pub term: String,
+    pub line: usize,
+    /// Defined as the short name of a party (`Acme Corp. ("Buyer")`).
+    pub party: bool,
+    /// Uses outside the definition, in the defined capitalization.
+    pub uses: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DraftCheckReport {
+    pub defined_terms: Vec<DefinedTerm>,
+    /// Numbered sections found, in document order.
+    pub sections: Vec<String>,
+    /// Issues ordered by line.
+    pub issues: Vec<DraftIssue>,
+}
+
+impl DraftCheckReport {
+    pub fn count(&self, severity: IssueSeverity) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == severity)
+            .count()
+    }
+}
+
+struct Line<'a> {
+    number: usize,
+    text: &'a str,
+}
+
+impl Line<'_> {
+    fn excerpt(&self) -> String {
+        let trimmed = self.text.trim();
+        if trimmed.chars().count() <= MAX_EXCERPT_CHARS {
+            return trimmed.to_string();
+        }
+        let cut: String = trimmed.chars().take(MAX_EXCERPT_CHARS).collect();
+        format!("{cut}…")
+    }
+
+    fn is_heading(&self) -> bool {
+        self.text.trim_start().starts_with('#')
+    }
+}
+
+fn issue(
+    check: &'static str,
+    severity: IssueSeverity,
+    line: &Line<'_>,
+    message: String,
+) -> DraftIssue {
+    DraftIssue {
+        check,
+        severity,
+        line: line.number,
+        excerpt: line.excerpt(),
+        message,
+    }
+}
+
+/// Lines outside fenced code blocks.
+fn prose_lines(text: &str) -> Vec<Line<'_>> {
+    let mut in_fence = false;
+    let mut lines = Vec::new();
+    for (idx, text) in text.lines().enumerate() {
+        if text.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if !in_fence {
+            lines.push(Line {
+                number: idx + 1,
+                text,
+            });
+        }
+    }
+    lines
+}
+
+/// `Parties` -> `Party`, `Notices` -> `Notice`.
+fn singular(term: &str) -> String {
+    if let Some(stem) = term.strip_suffix("ies") {
+        format!("{stem}y")
+    } else if let Some(stem) = term.strip_suffix('s').filter(|stem| !stem.ends_with('s')) {
+        stem.to_string()
+    } else {
+        term.to_string()
+    }
+}
+
+/// Matches of `term` (and its plural) on word boundaries, in any case.
+fn term_regex(term: &str) -> Regex {
+    let stem = regex::escape(&singular(term));
+    Regex::new(&format!(
+        r"(?i)\b{stem}(?:s|es)?\b|\b{}\b",
+        regex::escape(term)
+    ))
+    .expect("escaped term regex")
+}
+
+struct Definition {
+    term: String,
+    line: usize,
+    party: bool,
+    /// (line, byte range of the quoted term) for every definition.
+    spans: Vec<(usize, usize, usize)>,
+}
+
+fn find_definitions(lines: &[Line<'_>], issues: &mut Vec<DraftIssue>) -> Vec<Definition> {
+    let mut definitions: Vec<Definition> = Vec::new();
+    for line in lines {
+        for m in QUOTED_TERM_RE.captures_iter(line.text) {
+            let whole = m.get(0).expect("match");
+            let term = m[1].trim().to_string();
+            let before = &line.text[..whole.start()];
+            let open_paren = before
+                .rfind('(')
+                .filter(|open| !before[*open..].contains(')'));
+            let defined_by_verb = DEFINING_VERB_RE.is_match(&line.text[whole.end()..]);
+            if open_paren.is_none() && !defined_by_verb {
+                continue;
+            }
+            let party = open_paren.is_some_and(|open| {
+                let start = before[..open]
+                    .char_indices()
+                    .rev()
+                    .nth(120)
+                    .map_or(0, |(idx, _)| idx);
+                PARTY_CONTEXT_RE.is_match(&before[start..open])
+            });
+            let span = (line.number, whole.start(), whole.end());
+            match definitions.iter_mut().find(|d| d.term == term) {
+                Some(existing) => {
+                    issues.push(issue(
+                        "defined_term",
+                        IssueSeverity::Warning,
+                        line,
+                        format!(
+                            "\"{term}\" is defined again (first defined on line {})",
+                            existing.line
+                        ),
+                    ));
+                    existing.spans.push(span);
+                }
+                None => definitions.push(Definition {
+                    term,
+                    line: line.number,
+                    party,
+                    spans: vec![span],
+                }),
+            }
+        }
+    }
+    definitions
+}
+
+fn check_term_uses(
+    lines: &[Line<'_>],
+    definitions: &[Definition],
+    issues: &mut Vec<DraftIssue>,
+) -> Vec<DefinedTerm> {
+    let mut terms = Vec::new();
+    for definition in definitions {
+        let re = term_regex(&definition.term);
+        let singular_term = singular(&definition.term);
+        let mut uses = 0;
+        let mut lowercase_flagged = false;
+        for line in lines {
+            for m in re.find_iter(line.text) {
+                let inside_definition = definition.spans.iter().any(|(number, start, end)| {
+                    *number == line.number && m.start() >= *start && m.end() <= *end
+                });
+                if inside_definition {
+                    continue;
+                }
+                let found = m.as_str();
+                if found.starts_with(&singular_term) || found == definition.term {
+                    uses += 1;
+                    continue;
+                }
+                let lowercase = found.chars().next().is_some_and(char::is_lowercase);
+                let multiword = definition.term.contains(' ');
+                if lowercase_flagged || !lowercase || !(multiword || definition.party) {
+                    continue;
+                }
+                lowercase_flagged = true;
+                let (check, what) = if definition.party {
+                    ("party_name", "party name")
+                } else {
+                    ("defined_term", "defined term")
+                };
+                issues.push(issue(
+                    check,
+                    IssueSeverity::Warning,
+                    line,
+                    format!(
+                        "\"{found}\" should match the {what} \"{}\" (defined on line {})",
+                        definition.term, definition.line
+                    ),
+                ));
+            }
+        }
+        if uses == 0
+            && let Some(line) = lines.iter().find(|line| line.number == definition.line)
+        {
+            issues.push(issue(
+                "defined_term",
+                IssueSeverity::Warning,
+                line,
+                format!("\"{}\" is defined but never used", definition.term),
+            ));
+        }
+        terms.push(DefinedTerm {
+            term: definition.term.clone(),
+            line: definition.line,
+            party: definition.party,
+            uses,
+        });
+    }
+    terms
+}
+
+fn check_undefined_terms(
+    lines: &[Line<'_>],
+    definitions: &[Definition],
+    entity_stems: &BTreeSet<String>,
+    issues: &mut Vec<DraftIssue>,
+) {
+    let defined: Vec<String> = definitions.iter().map(|d| singular(&d.term)).collect();
+    let is_known = |candidate: &str| {
+        let candidate = singular(candidate);
+        defined.iter().any(|term| {
+            candidate == *term
+                || candidate
+                    .strip_prefix(term.as_str())
+                    .is_some_and(|rest| rest.starts_with(' '))
+        }) || COMMON_PROPER_TERMS
+            .iter()
+            .any(|common| candidate == singular(common) || candidate.ends_with(common))
+            || entity_stems.contains(&candidate.to_lowercase())
+    };
+    let mut undefined: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    for (idx, line) in lines.iter().enumerate() {
+        if line.is_heading() {
+            continue;
+        }
+        for m in TERM_USE_RE.captures_iter(line.text) {
+            let candidate = m[1].to_string();
+            if is_known(&candidate) {
+                continue;
+            }
+            undefined
+                .entry(candidate)
+                .and_modify(|(_, count)| *count += 1)
+                .or_insert((idx, 1));
+        }
+    }
+    for (term, (idx, count)) in undefined {
+        let times = if count == 1 {
+            "once".to_string()
+        } else {
+            format!("{count} times")
+        };
+        issues.push(issue(
+            "undefined_term",
+            IssueSeverity::Warning,
+            &lines[idx],
+            format!("\"{term}\" is used as a defined term ({times}) but never defined"),
+        ));
+    }
+}
+
+/// Flag parties written in more than one form; returns the lower-cased
+/// name stems so they are not reported as undefined terms.
+fn check_party_names(lines: &[Line<'_>], issues: &mut Vec<DraftIssue>) -> BTreeSet<String> {
+    const LEADING_WORDS: [&str; 6] = ["The", "This", "Such", "Each", "Said", "Any"];
+    // stem -> (first line index, surface forms in order)
+    let mut forms: BTreeMap<String, (usize, Vec<String>)> = BTreeMap::new();
+    for (idx, line) in lines.iter().enumerate() {
+        for m in ENTITY_RE.captures_iter(line.text) {
+            let mut words: Vec<&str> = m[1].split_whitespace().collect();
+            while words
+                .first()
+                .is_some_and(|word| LEADING_WORDS.contains(word))
+            {
+                words.remove(0);
+            }
+            if words.is_empty() {
+                continue;
+            }
+            let stem = words.join(" ").to_lowercase();
+            let surface = format!("{}{}{}", words.join(" "), &m[2], &m[3]);
+            let entry = forms.entry(stem).or_insert_with(|| (idx, Vec::new()));
+            if !entry
+                .1
+                .iter()
+                .any(|seen| seen.to_lowercase() == surface.to_lowercase())
+            {
+                entry.1.push(surface);
+            }
+        }
+    }
+    for (idx, variants) in forms.values() {
+        if variants.len() > 1 {
+            issues.push(issue(
+                "party_name",
+                IssueSeverity::Warning,
+                &lines[*idx],
+                format!("party is named inconsistently: {}", variants.join(" / ")),
+            ));
+        }
+    }
+    forms.into_keys().collect()
+}
+
+/// Numbered section headings: `## 4.2 Indemnity`, `Section 4.2.`,
+/// `4.2 Indemnity`, `1. Definitions`. When the draft has heading or
+/// `Section`-style numbers, single-level plain numbers (list items) are not
+/// counted.
+fn find_sections(lines: &[Line<'_>]) -> Vec<(usize, Vec<u32>)> {
+    let mut strong = Vec::new();
+    let mut weak = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        let Some(caps) = SECTION_LINE_RE.captures(line.text) else {
+            continue;
+        };
+        let raw = &caps[3];
+        let parts: Vec<u32> = raw
+            .split('.')
+            .filter_map(|part| part.parse().ok())
+            .collect();
+        if parts.is_empty() || parts[0] == 0 || parts[0] > 200 {
+            continue;
+        }
+        let prefixed = caps.get(1).is_some() || caps.get(2).is_some();
+        let dotted = parts.len() > 1 || &caps[4] == ".";
+        if prefixed {
+            strong.push((idx, parts));
+        } else if dotted {
+            weak.push((idx, parts));
+        }
+    }
+    if strong.is_empty() {
+        return weak;
+    }
+    let mut sections: Vec<(usize, Vec<u32>)> = strong
+        .into_iter()
+        .chain(weak.into_iter().filter(|(_, parts)| parts.len() > 1))
+        .collect();
+    sections.sort_by_key(|(idx, _)| *idx);
+    sections
+}
+
+fn section_label(parts: &[u32]) -> String {
+    parts
+        .iter()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn check_numbering(
+    lines: &[Line<'_>],
+    sections: &[(usize, Vec<u32>)],
+    issues: &mut Vec<DraftIssue>,
+) {
+    let mut last_child: HashMap<Vec<u32>, u32> = HashMap::new();
+    for (idx, parts) in sections {
+        let (last, parent) = parts.split_last().expect("non-empty section number");
+        let prefix = if parent.is_empty() {
+            String::new()
+        } else {
+            format!("{}.", section_label(parent))
+        };
+        let expected = last_child.get(parent).map_or(1, |prev| prev + 1);
+        let message = match last_child.get(parent) {
+            _ if *last == expected => None,
+            // A run starting again at 1 is a new list, not an error.
+            Some(_) if *last == 1 => None,
+            Some(prev) if last == prev => Some(format!(
+                "section {} is numbered twice",
+                section_label(parts)
+            )),
+            Some(prev) if last < prev => Some(format!(
+                "section {} follows {prefix}{prev}",
+                section_label(parts)
+            )),
+            _ => Some(format!(
+                "section {} skips {prefix}{expected}",
+                section_label(parts)
+            )),
+        };
+        if let Some(message) = message {
+            issues.push(issue(
+                "numbering",
+                IssueSeverity::Warning,
+                &lines[*idx],
+                message,
+            ));
+        }
+        last_child.insert(parent.to_vec(), *last);
+    }
+}
+
+fn check_cross_references(
+    lines: &[Line<'_>],
+    sections: &[(usize, Vec<u32>)],
+    issues: &mut Vec<DraftIssue>,
+) {
+    let known: BTreeSet<String> = sections
+        .iter()
+        .map(|(_, parts)| section_label(parts))
+        .collect();
+    let mut unchecked: Option<(usize, usize)> = None;
+    for (idx, line) in lines.iter().enumerate() {
+        if line.is_heading() {
+            continue;
+        }
+        for caps in SECTION_REF_RE.captures_iter(line.text) {
+            let whole = caps.get(0).expect("match");
+            let rest = &line.text[whole.end()..];
+            // `Section 78j`, `Section 4 of the Securities Act`: not ours.
+            if rest.chars().next().is_some_and(char::is_alphanumeric)
+                || EXTERNAL_REF_RE.is_match(rest)
+            {
+                continue;
+            }
+            for number in SECTION_NUMBER_RE.find_iter(&caps[1]) {
+                let label = number.as_str();
+                if known.is_empty() {
+                    let entry = unchecked.get_or_insert((idx, 0));
+                    entry.1 += 1;
+                    continue;
+                }
+                if !known.contains(label) {
+                    issues.push(issue(
+                        "cross_reference",
+                        IssueSeverity::Error,
+                        line,
+                        format!("reference to section {label}, which does not exist"),
+                    ));
+                }
+            }
+        }
+    }
+    if let Some((idx, count)) = unchecked {
+        issues.push(issue(
+            "cross_reference",
+            IssueSeverity::Warning,
+            &lines[idx],
+            format!("{count} section reference(s) not checked: the draft has no numbered sections"),
+        ));
+    }
+}
+
+fn check_typing(lines: &[Line<'_>], checks: &BTreeSet<&str>, issues: &mut Vec<DraftIssue>) {
+    for line in lines {
+        // Markdown tables align columns with spaces.
+        if checks.contains("double_space") && !line.text.trim_start().starts_with('|') {
+            let count = DOUBLE_SPACE_RE.find_iter(line.text.trim()).count();
+            if count > 0 {
+                issues.push(issue(
+                    "double_space",
+                    IssueSeverity::Warning,
+                    line,
+                    format!("{count} double space(s)"),
+                ));
+            }
+        }
+        if checks.contains("repeated_word") {
+            let words: Vec<_> = WORD_RE.find_iter(line.text).collect();
+            for pair in words.windows(2) {
+                let (first, second) = (pair[0], pair[1]);
+                if first.as_str().eq_ignore_ascii_case(second.as_str())
+                    && line.text[first.end()..second.start()].trim().is_empty()
+                    && !ALLOWED_REPEATS.contains(&first.as_str().to_lowercase().as_str())
+                {
+                    issues.push(issue(
+                        "repeated_word",
+                        IssueSeverity::Warning,
+                        line,
+                        format!("\"{} {}\" repeats a word", first.as_str(), second.as_str()),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Run `checks` (all of [`CHECKS`] when empty) over `text`.
+pub fn check_draft(text: &str, checks: &[&str]) -> Result<DraftCheckReport, String> {
+    if let Some(unknown) = checks.iter().find(|check| !CHECKS.contains(*check)) {
+        return Err(format!(
+            "unknown check '{unknown}' (expected one of: {})",
+            CHECKS.join(", ")
+        ));
+    }
+    let enabled: BTreeSet<&str> = if checks.is_empty() {
+        CHECKS.into_iter().collect()
+    } else {
+        checks.iter().copied().collect()
+    };
+    let lines = prose_lines(text);
+    let mut issues = Vec::new();
+
+    let mut definition_issues = Vec::new();
+    let definitions = find_definitions(&lines, &mut definition_issues);
+    let mut term_issues = Vec::new();
+    let defined_terms = check_term_uses(&lines, &definitions, &mut term_issues);
+    definition_issues.extend(term_issues);
+    issues.extend(
+        definition_issues
+            .into_iter()
+            .filter(|issue| enabled.contains(issue.check)),
+    );
+
+    let mut party_issues = Vec::new();
+    let entity_stems = check_party_names(&lines, &mut party_issues);
+    if enabled.contains("party_name") {
+        issues.extend(party_issues);
+    }
+    if enabled.contains("undefined_term") {
+        check_undefined_terms(&lines, &definitions, &entity_stems, &mut issues);
+    }
+
+    let sections = find_sections(&lines);
+    if enabled.contains("numbering") {
+        check_numbering(&lines, &sections, &mut issues);
+    }
+    if enabled.contains("cross_reference") {
+        check_cross_references(&lines, &sections, &mut issues);
+    }
+    check_typing(&lines, &enabled, &mut issues);
+
+    issues.sort_by_key(|issue| issue.line);
+    Ok(DraftCheckReport {
+        defined_terms,
+        sections: sections
+            .iter()
+            .map(|(_, parts)| section_label(parts))
+            .collect(),
+        issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CHECKS, check_draft};
+    use crate::legal::filing_validation::IssueSeverity;
+
+    const DRAFT: &str = "\
+# Asset Purchase Agreement
+
+This Asset Purchase Agreement (the \"Agreement\") is made by Acme Corp., a Delaware \
+corporation (\"Buyer\"), and Globex LLC (\"Seller\").
+
+## 1. Definitions
+
+\"Purchase Price\" means the amount set out in Section 2.1.
+\"Escrow Agent\" means First Bank.
+
+## 2. Purchase
+
+2.1 Buyer shall pay the Purchase Price at the Closing Date.
+2.3 The buyer may assign this Agreement to an affiliate of Acme Corporation.
+
+## 4. Indemnity
+
+Seller shall indemnify Buyer as set out in Section 4.2 and Section 10 of the Securities Act.
+The  the purchase price is final.
+
+```
+Section 9.9 inside a code block is ignored.
+```
+";
+
+    fn issues<'a>(
+        report: &'a super::DraftCheckReport,
+        check: &str,
+    ) -> Vec<(usize, &'a str, IssueSeverity)> {
+        report
+            .issues
+            .iter()
+            .filter(|issue| issue.check == check)
+            .map(|issue| (issue.line, issue.message.as_str(), issue.severity))
+            .collect()
+    }
+
+    #[test]
+    fn defined_terms_and_parties() {
+        let report = check_draft(DRAFT, &[]).unwrap();
+        let terms: Vec<(&str, bool)> = report
+            .defined_terms
+            .iter()
+            .map(|term| (term.term.as_str(), term.party))
+            .collect();
+        assert_eq!(
+            terms,
+            vec![
+                ("Agreement", false),
+                ("Buyer", true),
+                ("Seller", true),
+                ("Purchase Price", false),
+                ("Escrow Agent", false),
+            ]
+        );
+
+        assert_eq!(
+            issues(&report, "undefined_term"),
+            vec![(
+                12,
+                "\"Closing Date\" is used as a defined term (once) but never defined",
+                IssueSeverity::Warning
+            )]
+        );
+        let defined = issues(&report, "defined_term");
+        assert_eq!(defined.len(), 2, "{defined:?}");
+        assert_eq!(defined[0].0, 8);
+        assert!(
+            defined[0]
+                .1
+                .contains("\"Escrow Agent\" is defined but never used")
+        );
+        assert_eq!(defined[1].0, 18);
+        assert!(defined[1].1.contains("\"purchase price\" should match"));
+
+        let parties = issues(&report, "party_name");
+        assert_eq!(parties.len(), 2, "{parties:?}");
+        assert_eq!(parties[0].0, 3);
+        assert!(parties[0].1.contains("Acme Corp. / Acme Corporation"));
+        assert_eq!(parties[1].0, 13);
+        assert!(
+            parties[1]
+                .1
+                .contains("\"buyer\" should match the party name \"Buyer\"")
+        );
+    }
+
+    #[test]
+    fn sections_references_and_typing() {
+        let report = check_draft(DRAFT, &[]).unwrap();
+        assert_eq!(report.sections, vec!["1", "2", "2.1", "2.3", "4"]);
+        assert_eq!(
+            issues(&report, "numbering"),
+            vec![
+                (13, "section 2.3 skips 2.2", IssueSeverity::Warning),
+                (15, "section 4 skips 3", IssueSeverity::Warning),
+            ]
+        );
+        assert_eq!(
+            issues(&report, "cross_reference"),
+            vec![(
+                17,
+                "reference to section 4.2, which does not exist",
+                IssueSeverity::Error
+            )]
+        );
+        assert_eq!(
+            issues(&report, "double_space"),
+            vec![(18, "1 double space(s)", IssueSeverity::Warning)]
+        );
+        assert_eq!(
+            issues(&report, "repeated_word"),
+            vec![(18, "\"The the\" repeats a word", IssueSeverity::Warning)]
+        );
+
+        let only = check_draft(DRAFT, &["cross_reference"]).unwrap();
+        assert!(
+            only.issues
+                .iter()
+                .all(|issue| issue.check == "cross_reference")
+        );
+        assert!(check_draft(DRAFT, &["spelling"]).is_err());
+        assert_eq!(CHECKS.len(), 7);
+
+        let unnumbered = check_draft("See Section 3 for details.", &[]).unwrap();
+        assert_eq!(
+            issues(&unnumbered, "cross_reference"),
+            vec![(
+                1,
+                "1 section reference(s) not checked: the draft has no numbered sections",
+                IssueSeverity::Warning
+            )]
+        );
+    }
+}
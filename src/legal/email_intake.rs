@@ -0,0 +1,218 @@
+//! Email-to-matter filing: which matter an inbound email belongs to.
+//!
+//! Mail for a matter names it in a recipient address, either as a plus tag
+//! (`intake+2024-0012@firm.example`) or as a `matter-` local part
+//! (`matter-2024-0012@firm.example`), or in a subject tag
+//! (`Re: Disclosure [Matter: 2024-0012]` or `[#2024-0012]`). Candidates are
+//! returned in that order; the gateway files the message under the first one
+//! that names an existing matter.
+//!
+//! The webhook is configured by the `email_intake` setting: the secret token
+//! in its URL and, optionally, the firm domains whose addresses are read.
+
+use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+
+/// Setting key holding [`EmailIntakeSettings`].
+pub const EMAIL_INTAKE_SETTING_KEY: &str = "email_intake";
+
+/// Local-part prefix of per-matter intake addresses.
+pub const MATTER_LOCAL_PART_PREFIX: &str = "matter-";
+
+const MIN_TOKEN_CHARS: usize = 24;
+const MAX_TOKEN_CHARS: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmailIntakeSettings {
+    /// Secret path segment of `/api/email/inbound/{token}`.
+    pub token: String,
+    /// Domains whose recipient addresses name matters. Empty reads every
+    /// recipient.
+    #[serde(default)]
+    pub domains: Vec<String>,
+}
+
+impl EmailIntakeSettings {
+    pub fn accepts_token(&self, token: &str) -> bool {
+        bool::from(self.token.as_bytes().ct_eq(token.as_bytes()))
+    }
+}
+
+/// Parse and validate an `email_intake` setting value.
+pub fn parse_setting_value(value: &serde_json::Value) -> Result<EmailIntakeSettings, String> {
+    let mut settings: EmailIntakeSettings = serde_json::from_value(value.clone())
+        .map_err(|e| format!("invalid email intake settings: {e}"))?;
+    if !(MIN_TOKEN_CHARS..=MAX_TOKEN_CHARS).contains(&settings.token.len())
+        || !settings
+            .token
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "token must be {MIN_TOKEN_CHARS}-{MAX_TOKEN_CHARS} letters, digits, '-' or '_'"
+        ));
+    }
+    settings.domains = settings
+        .domains
+        .iter()
+        .map(|domain| domain.trim().trim_start_matches('@').to_ascii_lowercase())
+        .collect();
+    if let Some(bad) = settings
+        .domains
+        .iter()
+        .find(|domain| domain.is_empty() || !domain.contains('.') || domain.contains('@'))
+    {
+        return Err(format!("invalid domain '{bad}'"));
+    }
+    Ok(settings)
+}
+
+/// The bare, lowercased `local@domain` of an address such as
+/// `"Jane Doe" <jane@firm.example>`.
+pub fn bare_address(raw: &str) -> Option<String> {
+    let inner = match (raw.rfind('<'), raw.rfind('>')) {
+        (Some(open), Some(close)) if open < close => &raw[open + 1..close],
+        _ => raw,
+    };
+    let address = inner.trim().to_ascii_lowercase();
+    crate::legal::correspondence::is_plausible_address(&address).then_some(address)
+}
+
+/// Matter ids named by one recipient address, most specific first.
+pub fn address_candidates(address: &str, domains: &[String]) -> Vec<String> {
+    let Some(address) = bare_address(address) else {
+        return Vec::new();
+    };
+    let Some((local, domain)) = address.split_once('@') else {
+        return Vec::new();
+    };
+    if !domains.is_empty() && !domains.iter().any(|d| d == domain) {
+        return Vec::new();
+    }
+    if let Some((_, tag)) = local.split_once('+') {
+        return vec![tag.to_string()];
+    }
+    match local.strip_prefix(MATTER_LOCAL_PART_PREFIX) {
+        Some(rest) if !rest.is_empty() => vec![local.to_string(), rest.to_string()],
+        _ => Vec::new(),
+    }
+}
+
+/// Matter ids tagged in a subject as `[Matter: id]`, `[Matter id]`, or
+/// `[#id]`. Other bracketed text (`[EXTERNAL]`) is ignored.
+pub fn subject_candidates(subject: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut rest = subject;
+    while let Some(open) = rest.find('[') {
+        let after = &rest[open + 1..];
+        let Some(close) = after.find(']') else {
+            break;
+        };
+        let tag = after[..close].trim();
+        let id = if let Some(id) = tag.strip_prefix('#') {
+            Some(id)
+        } else if tag
+            .get(..6)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("matter"))
+        {
+            let id = &tag[6..];
+            id.strip_prefix(':')
+                .or_else(|| id.strip_prefix(' '))
+                .or_else(|| id.strip_prefix('#'))
+        } else {
+            None
+        };
+        if let Some(id) = id.map(str::trim).filter(|id| !id.is_empty()) {
+            out.push(id.to_string());
+        }
+        rest = &after[close + 1..];
+    }
+    out
+}
+
+/// Sanitized, de-duplicated matter id candidates for an email: recipient
+/// addresses first, then subject tags.
+pub fn matter_candidates(recipients: &[String], subject: &str, domains: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    let raw = recipients
+        .iter()
+        .flat_map(|address| address_candidates(address, domains))
+        .chain(subject_candidates(subject));
+    for candidate in raw {
+        let sanitized = crate::legal::policy::sanitize_matter_id(&candidate);
+        if !sanitized.is_empty() && !out.contains(&sanitized) {
+            out.push(sanitized);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plus_and_matter_addresses_name_matters() {
+        let domains = vec!["firm.example".to_string()];
+        assert_eq!(
+            address_candidates("Intake <intake+2024-0012@Firm.example>", &domains),
+            vec!["2024-0012"]
+        );
+        assert_eq!(
+            address_candidates("matter-2024-0012@firm.example", &domains),
+            vec!["matter-2024-0012", "2024-0012"]
+        );
+        assert!(address_candidates("matter-2024-0012@other.example", &domains).is_empty());
+        assert!(address_candidates("reception@firm.example", &domains).is_empty());
+        assert_eq!(
+            address_candidates("matter-acme@other.example", &[]),
+            vec!["matter-acme", "acme"]
+        );
+    }
+
+    #[test]
+    fn subject_tags_name_matters() {
+        assert_eq!(
+            subject_candidates("[EXTERNAL] Re: Disclosure [Matter: 2024-0012] [#acme v foo]"),
+            vec!["2024-0012", "acme v foo"]
+        );
+        assert_eq!(
+            subject_candidates("[matter 7] [matter#8] [Matters]"),
+            vec!["7", "8"]
+        );
+        assert!(subject_candidates("Re: [unclosed").is_empty());
+    }
+
+    #[test]
+    fn candidates_are_sanitized_and_ordered() {
+        let recipients = vec![
+            "reception@firm.example".to_string(),
+            "intake+Acme_v_Foo@firm.example".to_string(),
+        ];
+        assert_eq!(
+            matter_candidates(&recipients, "Docs [Matter: Acme v. Foo] [#acme_v_foo]", &[]),
+            vec!["acme_v_foo", "acme-v--foo"]
+        );
+    }
+
+    #[test]
+    fn setting_value_is_validated() {
+        let settings = parse_setting_value(&serde_json::json!({
+            "token": "abcdefghijklmnopqrstuvwx",
+            "domains": [" @Firm.Example "],
+        }))
+        .expect("valid settings");
+        assert_eq!(settings.domains, vec!["firm.example"]);
+        assert!(settings.accepts_token("abcdefghijklmnopqrstuvwx"));
+        assert!(!settings.accepts_token("abcdefghijklmnopqrstuvwy"));
+
+        assert!(parse_setting_value(&serde_json::json!({ "token": "short" })).is_err());
+        assert!(
+            parse_setting_value(&serde_json::json!({
+                "token": "abcdefghijklmnopqrstuvwx",
+                "domains": ["localhost"],
+            }))
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,859 @@
+//! Named-entity extraction for the conflict graph.
+//!
+//! Person and organization names found in ingested matter documents are
+//! queued as party candidates (with the source path and a snippet as
+//! provenance) so conflicts buried inside uploaded contracts surface even
+//! when nobody typed the names into intake. Candidates only join the
+//! conflict graph once a reviewer accepts them.
+//!
+//! The same pass records every person, organization, date, and amount in
+//! the document entity store, which backs the per-matter party graph.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::db::{
+    ConflictHit, CreateDocumentEntityParams, Database, DocumentEntityKind, DocumentEntityRecord,
+    MatterPartyRecord, PartyCandidateRecord, PartyCandidateStatus, PartyEntityType,
+    PartyRelationshipRecord, UpsertPartyCandidateParams, normalize_party_name,
+};
+use crate::error::DatabaseError;
+use crate::llm::{ChatMessage, CompletionRequest, LlmProvider, LlmTask};
+
+/// Characters of document content sent to the LLM extractor.
+const LLM_EXCERPT_CHARS: usize = 8_000;
+
+/// Upper bound on candidates queued from a single document.
+const MAX_ENTITIES_PER_DOCUMENT: usize = 50;
+
+/// Upper bound on dates and amounts recorded from a single document.
+const MAX_VALUES_PER_DOCUMENT: usize = 100;
+
+/// Upper bound on edges returned in a party graph.
+const MAX_GRAPH_EDGES: usize = 500;
+
+const SNIPPET_CHARS: usize = 160;
+
+static ORGANIZATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\b((?:[A-Z][A-Za-z0-9'&\-]*,?[ \t]+(?:(?:&|and|of|the)[ \t]+)?){1,6}(?:Inc|Incorporated|LLC|L\.L\.C|Ltd|Limited|Corp|Corporation|Company|LLP|LP|PLC|GmbH|AG|S\.A|N\.V|B\.V|Holdings|Group|Partners|Bank)\b\.?)",
+    )
+    .expect("valid organization regex")
+});
+
+static HONORIFIC_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\b(?:Mr|Mrs|Ms|Miss|Dr|Prof|Hon)\.?[ \t]+([A-Z][a-z]+(?:[ \t]+[A-Z]\.)?(?:[ \t]+[A-Z][A-Za-z'\-]+){1,2})\b",
+    )
+    .expect("valid honorific regex")
+});
+
+/// "$1,250.00", "US$ 3 million", "EUR 500", "£12,000".
+static AMOUNT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?:(US\$|CA?\$|€|£|\$)[ \t]?|\b(USD|CAD|EUR|GBP)[ \t]?)(\d{1,3}(?:,\d{3})+|\d+)(?:\.(\d{1,2}))?(?:[ \t]+(thousand|million|billion))?\b",
+    )
+    .expect("valid amount regex")
+});
+
+static SIGNATURE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?m)^[ \t]*(?:Name|By|Signed|Per|Witness)[ \t]*:[ \t]*(?:/s/[ \t]*)?([A-Z][a-z]+(?:[ \t]+[A-Z]\.?)?(?:[ \t]+[A-Z][A-Za-z'\-]+){1,2})[ \t]*$",
+    )
+    .expect("valid signature regex")
+});
+
+/// Capitalized words that start sentences or clauses and get swept into an
+/// organization match ("WHEREAS Acme Corp", "The Acme Group").
+const LEADING_STOPWORDS: &[&str] = &[
+    "the",
+    "this",
+    "that",
+    "whereas",
+    "between",
+    "and",
+    "by",
+    "with",
+    "from",
+    "to",
+    "dear",
+    "re",
+    "attention",
+    "attn",
+    "cc",
+    "plaintiff",
+    "defendant",
+    "claimant",
+    "respondent",
+    "client",
+];
+
+/// An entity found in a document, before it is queued for review.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedEntity {
+    pub name: String,
+    pub entity_type: PartyEntityType,
+    /// Line of text the entity was found in.
+    pub snippet: Option<String>,
+    pub confidence: f32,
+    pub extractor: &'static str,
+}
+
+fn snippet_around(content: &str, start: usize) -> Option<String> {
+    let line_start = content[..start].rfind('\n').map_or(0, |idx| idx + 1);
+    let line_end = content[start..]
+        .find('\n')
+        .map_or(content.len(), |idx| start + idx);
+    let line = content[line_start..line_end].trim();
+    if line.is_empty() {
+        return None;
+    }
+    Some(line.chars().take(SNIPPET_CHARS).collect())
+}
+
+fn strip_leading_stopwords(name: &str) -> &str {
+    let mut rest = name.trim();
+    while let Some((first, tail)) = rest.split_once(char::is_whitespace) {
+        let word = first.trim_end_matches(',').to_ascii_lowercase();
+        if !LEADING_STOPWORDS.contains(&word.as_str()) {
+            break;
+        }
+        rest = tail.trim_start();
+    }
+    rest
+}
+
+/// Drop a sentence-ending period unless it belongs to an abbreviated
+/// suffix ("Acme Inc.", "Globex S.A.").
+fn trim_sentence_period(name: &str) -> &str {
+    let Some(stem) = name.strip_suffix('.') else {
+        return name;
+    };
+    let last = stem.rsplit(char::is_whitespace).next().unwrap_or(stem);
+    if matches!(last, "Inc" | "Ltd" | "Corp" | "Co") || last.contains('.') {
+        name
+    } else {
+        stem
+    }
+}
+
+/// Merge by normalized name, keeping the highest-confidence sighting.
+fn dedupe_entities(entities: Vec<ExtractedEntity>) -> Vec<ExtractedEntity> {
+    let mut order: Vec<String> = Vec::new();
+    let mut by_name: HashMap<String, ExtractedEntity> = HashMap::new();
+    for entity in entities {
+        let key = normalize_party_name(&entity.name);
+        if key.is_empty() {
+            continue;
+        }
+        match by_name.get_mut(&key) {
+            Some(existing) => {
+                if entity.confidence > existing.confidence {
+                    *existing = ExtractedEntity {
+                        snippet: entity.snippet.or(existing.snippet.take()),
+                        ..entity
+                    };
+                }
+            }
+            None => {
+                order.push(key.clone());
+                by_name.insert(key, entity);
+            }
+        }
+    }
+    order
+        .into_iter()
+        .filter_map(|key| by_name.remove(&key))
+        .take(MAX_ENTITIES_PER_DOCUMENT)
+        .collect()
+}
+
+/// Deterministic extraction: company-suffix names, honorific-prefixed
+/// names, and signature-block lines.
+pub fn extract_entities_heuristic(content: &str) -> Vec<ExtractedEntity> {
+    let mut out = Vec::new();
+
+    for captures in ORGANIZATION_RE.captures_iter(content) {
+        let Some(m) = captures.get(1) else { continue };
+        let name = trim_sentence_period(strip_leading_stopwords(m.as_str()));
+        // A bare suffix ("Limited") is not a name.
+        if !name.contains(char::is_whitespace) {
+            continue;
+        }
+        out.push(ExtractedEntity {
+            name: name.to_string(),
+            entity_type: PartyEntityType::Organization,
+            snippet: snippet_around(content, m.start()),
+            confidence: 0.8,
+            extractor: "heuristic",
+        });
+    }
+
+    for (re, confidence) in [(&*SIGNATURE_RE, 0.75), (&*HONORIFIC_RE, 0.7)] {
+        for captures in re.captures_iter(content) {
+            let Some(m) = captures.get(1) else { continue };
+            out.push(ExtractedEntity {
+                name: m.as_str().trim().to_string(),
+                entity_type: PartyEntityType::Person,
+                snippet: snippet_around(content, m.start()),
+                confidence,
+                extractor: "heuristic",
+            });
+        }
+    }
+
+    dedupe_entities(out)
+}
+
+#[derive(Debug, Deserialize)]
+struct LlmEntity {
+    name: String,
+    #[serde(rename = "type")]
+    entity_type: String,
+    #[serde(default)]
+    confidence: Option<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LlmEntities {
+    #[serde(default)]
+    entities: Vec<LlmEntity>,
+}
+
+fn parse_llm_entities(raw: &str, content: &str) -> Option<Vec<ExtractedEntity>> {
+    let start = raw.find('{')?;
+    let end = raw.rfind('}')?;
+    let parsed: LlmEntities = serde_json::from_str(raw.get(start..=end)?).ok()?;
+    Some(
+        parsed
+            .entities
+            .into_iter()
+            .filter_map(|entity| {
+                let entity_type = match entity.entity_type.trim().to_ascii_lowercase().as_str() {
+                    "person" | "individual" => PartyEntityType::Person,
+                    "organization" | "organisation" | "company" | "entity" => {
+                        PartyEntityType::Organization
+                    }
+                    _ => return None,
+                };
+                let name = entity.name.trim().to_string();
+                if normalize_party_name(&name).is_empty() {
+                    return None;
+                }
+                Some(ExtractedEntity {
+                    snippet: content
+                        .find(&name)
+                        .and_then(|idx| snippet_around(content, idx)),
+                    name,
+                    entity_type,
+                    confidence: entity.confidence.unwrap_or(0.85).clamp(0.0, 1.0),
+                    extractor: "llm",
+                })
+            })
+            .collect(),
+    )
+}
+
+async fn extract_with_llm(
+    llm: &dyn LlmProvider,
+    path: &str,
+    content: &str,
+) -> Option<Vec<ExtractedEntity>> {
+    let excerpt: String = content.chars().take(LLM_EXCERPT_CHARS).collect();
+    let prompt = format!(
+        r#"List every person and organization named in this legal document that
+could be a party, signatory, affiliate, or witness. Skip courts, statutes,
+places, and generic roles ("the Buyer").
+
+File name: {path}
+
+Content excerpt:
+---
+{excerpt}
+---
+
+Respond with JSON only:
+{{"entities": [{{"name": "<full name>", "type": "person|organization", "confidence": 0.0-1.0}}]}}"#
+    );
+    let request = CompletionRequest::new(vec![ChatMessage::user(prompt)])
+        .with_max_tokens(800)
+        .with_temperature(0.0)
+        .with_task(LlmTask::Extraction);
+    let response = match llm.complete(request).await {
+        Ok(response) => response,
+        Err(err) => {
+            tracing::debug!("LLM entity extraction failed for '{path}': {err}");
+            return None;
+        }
+    };
+    let entities = parse_llm_entities(&response.content, content);
+    if entities.is_none() {
+        tracing::debug!("LLM entity extraction for '{path}' returned unparseable output");
+    }
+    entities
+}
+
+/// Extract entities at ingestion.
+///
+/// The heuristic always runs; when an LLM is available its results are
+/// merged in, since a missed name is costlier here than an extra candidate
+/// for the reviewer to reject.
+pub async fn extract_entities(
+    llm: Option<&dyn LlmProvider>,
+    path: &str,
+    content: &str,
+) -> Vec<ExtractedEntity> {
+    let mut entities = extract_entities_heuristic(content);
+    if let Some(llm) = llm
+        && let Some(found) = extract_with_llm(llm, path, content).await
+    {
+        entities.extend(found);
+        entities = dedupe_entities(entities);
+    }
+    entities
+}
+
+/// Queue extracted entities as pending party candidates for a matter.
+///
+/// Names already recorded as parties (or aliases) on the matter are skipped.
+pub async fn queue_party_candidates(
+    store: &dyn Database,
+    matter_id: &str,
+    source_path: &str,
+    entities: &[ExtractedEntity],
+) -> Result<Vec<PartyCandidateRecord>, DatabaseError> {
+    let known: HashSet<String> = store
+        .list_matter_parties(matter_id)
+        .await?
+        .into_iter()
+        .flat_map(|party| std::iter::once(party.name).chain(party.aliases))
+        .map(|name| normalize_party_name(&name))
+        .collect();
+
+    let mut queued = Vec::new();
+    for entity in entities {
+        if known.contains(&normalize_party_name(&entity.name)) {
+            continue;
+        }
+        let record = store
+            .upsert_party_candidate(
+                matter_id,
+                &UpsertPartyCandidateParams {
+                    name: entity.name.clone(),
+                    entity_type: entity.entity_type,
+                    source_path: source_path.to_string(),
+                    snippet: entity.snippet.clone(),
+                    confidence: f64::from(entity.confidence),
+                    extractor: entity.extractor.to_string(),
+                },
+            )
+            .await?;
+        queued.push(record);
+    }
+    Ok(queued)
+}
+
+/// Normalize a matched amount to `CUR 1234.56`. A bare `$` keeps the
+/// symbol since the currency cannot be told from the text alone.
+fn normalize_amount(captures: &regex::Captures<'_>) -> Option<String> {
+    let currency = match (captures.get(1), captures.get(2)) {
+        (Some(symbol), _) => match symbol.as_str() {
+            "US$" => "USD",
+            "C$" | "CA$" => "CAD",
+            "€" => "EUR",
+            "£" => "GBP",
+            _ => "$",
+        },
+        (None, Some(code)) => code.as_str(),
+        (None, None) => return None,
+    };
+    let whole: u128 = captures.get(3)?.as_str().replace(',', "").parse().ok()?;
+    let cents: u128 = match captures.get(4).map(|m| m.as_str()) {
+        Some(frac) if frac.len() == 1 => frac.parse::<u128>().ok()? * 10,
+        Some(frac) => frac.parse().ok()?,
+        None => 0,
+    };
+    let multiplier: u128 = match captures.get(5).map(|m| m.as_str()) {
+        Some("thousand") => 1_000,
+        Some("million") => 1_000_000,
+        Some("billion") => 1_000_000_000,
+        _ => 1,
+    };
+    let total = (whole * 100 + cents).checked_mul(multiplier)?;
+    Some(format!("{currency} {}.{:02}", total / 100, total % 100))
+}
+
+/// Build the document entity rows for one document: the extracted people
+/// and organizations plus every date and amount in `content`.
+pub fn document_entities(
+    content: &str,
+    parties: &[ExtractedEntity],
+) -> Vec<CreateDocumentEntityParams> {
+    let mut out: Vec<CreateDocumentEntityParams> = parties
+        .iter()
+        .map(|entity| CreateDocumentEntityParams {
+            kind: entity.entity_type.into(),
+            value: entity.name.clone(),
+            normalized_value: normalize_party_name(&entity.name),
+            snippet: entity.snippet.clone(),
+            confidence: f64::from(entity.confidence),
+            extractor: entity.extractor.to_string(),
+        })
+        .collect();
+
+    let mut seen: HashSet<(DocumentEntityKind, String)> = HashSet::new();
+    let mut values = Vec::new();
+    for mention in crate::legal::chronology::find_dates(content) {
+        let normalized = mention.date.format("%Y-%m-%d").to_string();
+        if seen.insert((DocumentEntityKind::Date, normalized.clone())) {
+            values.push(CreateDocumentEntityParams {
+                kind: DocumentEntityKind::Date,
+                value: content[mention.start..mention.end].to_string(),
+                normalized_value: normalized,
+                snippet: snippet_around(content, mention.start),
+                confidence: f64::from(mention.confidence),
+                extractor: "heuristic".to_string(),
+            });
+        }
+    }
+    for captures in AMOUNT_RE.captures_iter(content) {
+        let (Some(m), Some(normalized)) = (captures.get(0), normalize_amount(&captures)) else {
+            continue;
+        };
+        if seen.insert((DocumentEntityKind::Amount, normalized.clone())) {
+            values.push(CreateDocumentEntityParams {
+                kind: DocumentEntityKind::Amount,
+                value: m.as_str().trim().to_string(),
+                normalized_value: normalized,
+                snippet: snippet_around(content, m.start()),
+                confidence: 0.8,
+                extractor: "heuristic".to_string(),
+            });
+        }
+    }
+    values.truncate(MAX_VALUES_PER_DOCUMENT);
+    out.extend(values);
+    out
+}
+
+/// Conflict-graph hits on other matters for newly queued candidates.
+///
+/// Only pending candidates are checked; reviewed ones already live in (or
+/// were deliberately kept out of) the graph.
+pub async fn candidate_conflict_hits(
+    store: &dyn Database,
+    matter_id: &str,
+    candidates: &[PartyCandidateRecord],
+    limit: usize,
+) -> Result<Vec<ConflictHit>, DatabaseError> {
+    let names: Vec<String> = candidates
+        .iter()
+        .filter(|candidate| candidate.status == PartyCandidateStatus::Pending)
+        .map(|candidate| candidate.name.clone())
+        .collect();
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut hits = store.find_conflict_hits_for_names(&names, limit).await?;
+    hits.retain(|hit| hit.matter_id != matter_id);
+    Ok(hits)
+}
+
+/// A person or organization in a matter's party graph, keyed by its
+/// normalized name.
+#[derive(Debug, Clone, Serialize)]
+pub struct PartyGraphNode {
+    pub id: String,
+    pub name: String,
+    /// `person` or `organization` when known from extraction.
+    pub entity_type: Option<String>,
+    /// Role on the matter for recorded parties.
+    pub role: Option<String>,
+    /// `party` (recorded on the matter), `candidate` (awaiting review),
+    /// `mentioned` (seen in a document only), or `related` (reached through
+    /// a recorded relationship).
+    pub status: &'static str,
+    /// Documents the node is mentioned in.
+    pub documents: Vec<String>,
+}
+
+/// A recorded relationship (with its recorded `kind`) or a
+/// `mentioned_with` co-occurrence within the listed documents.
+#[derive(Debug, Clone, Serialize)]
+pub struct PartyGraphEdge {
+    pub source: String,
+    pub target: String,
+    pub kind: String,
+    pub documents: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PartyGraph {
+    pub nodes: Vec<PartyGraphNode>,
+    pub edges: Vec<PartyGraphEdge>,
+}
+
+/// Assemble a matter's party graph from its recorded parties and
+/// relationships, its party candidates, and the people and organizations
+/// recorded in its documents. Rejected candidates are left out.
+pub fn build_party_graph(
+    parties: &[MatterPartyRecord],
+    relationships: &[PartyRelationshipRecord],
+    candidates: &[PartyCandidateRecord],
+    entities: &[DocumentEntityRecord],
+) -> PartyGraph {
+    let mut nodes: BTreeMap<String, PartyGraphNode> = BTreeMap::new();
+    // Alias spellings resolve to their party's node.
+    let mut key_for: HashMap<String, String> = HashMap::new();
+    let mut documents: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+    for party in parties {
+        let key = normalize_party_name(&party.name);
+        if key.is_empty() {
+            continue;
+        }
+        for alias in &party.aliases {
+            key_for.insert(normalize_party_name(alias), key.clone());
+        }
+        key_for.insert(key.clone(), key.clone());
+        nodes.entry(key.clone()).or_insert(PartyGraphNode {
+            id: key,
+            name: party.name.clone(),
+            entity_type: None,
+            role: Some(party.role.as_str().to_string()),
+            status: "party",
+            documents: Vec::new(),
+        });
+    }
+
+    let rejected: HashSet<String> = candidates
+        .iter()
+        .filter(|candidate| candidate.status == PartyCandidateStatus::Rejected)
+        .map(|candidate| normalize_party_name(&candidate.name))
+        .collect();
+    for candidate in candidates {
+        let normalized = normalize_party_name(&candidate.name);
+        if normalized.is_empty() || rejected.contains(&normalized) {
+            continue;
+        }
+        let key = key_for.get(&normalized).cloned().unwrap_or(normalized);
+        let node = nodes.entry(key.clone()).or_insert(PartyGraphNode {
+            id: key.clone(),
+            name: candidate.name.clone(),
+            entity_type: None,
+            role: None,
+            status: "candidate",
+            documents: Vec::new(),
+        });
+        node.entity_type
+            .get_or_insert_with(|| candidate.entity_type.as_str().to_string());
+        documents
+            .entry(candidate.source_path.clone())
+            .or_default()
+            .insert(key);
+    }
+
+    for entity in entities.iter().filter(|entity| entity.kind.is_party()) {
+        if entity.normalized_value.is_empty() || rejected.contains(&entity.normalized_value) {
+            continue;
+        }
+        let key = key_for
+            .get(&entity.normalized_value)
+            .cloned()
+            .unwrap_or_else(|| entity.normalized_value.clone());
+        let node = nodes.entry(key.clone()).or_insert(PartyGraphNode {
+            id: key.clone(),
+            name: entity.value.clone(),
+            entity_type: None,
+            role: None,
+            status: "mentioned",
+            documents: Vec::new(),
+        });
+        node.entity_type
+            .get_or_insert_with(|| entity.kind.as_str().to_string());
+        documents
+            .entry(entity.source_path.clone())
+            .or_default()
+            .insert(key);
+    }
+
+    let mut edges = Vec::new();
+    for relationship in relationships {
+        let mut ends = Vec::with_capacity(2);
+        for name in [&relationship.parent_name, &relationship.child_name] {
+            let normalized = normalize_party_name(name);
+            let key = key_for.get(&normalized).cloned().unwrap_or(normalized);
+            nodes.entry(key.clone()).or_insert(PartyGraphNode {
+                id: key.clone(),
+                name: name.clone(),
+                entity_type: None,
+                role: None,
+                status: "related",
+                documents: Vec::new(),
+            });
+            ends.push(key);
+        }
+        edges.push(PartyGraphEdge {
+            source: ends[0].clone(),
+            target: ends[1].clone(),
+            kind: relationship.kind.clone(),
+            documents: Vec::new(),
+        });
+    }
+
+    let mut co_mentions: BTreeMap<(String, String), BTreeSet<String>> = BTreeMap::new();
+    for (path, keys) in &documents {
+        for key in keys {
+            if let Some(node) = nodes.get_mut(key) {
+                node.documents.push(path.clone());
+            }
+        }
+        let keys: Vec<&String> = keys.iter().collect();
+        for (idx, source) in keys.iter().enumerate() {
+            for target in &keys[idx + 1..] {
+                co_mentions
+                    .entry(((*source).clone(), (*target).clone()))
+                    .or_default()
+                    .insert(path.clone());
+            }
+        }
+    }
+    edges.extend(
+        co_mentions
+            .into_iter()
+            .map(|((source, target), docs)| PartyGraphEdge {
+                source,
+                target,
+                kind: "mentioned_with".to_string(),
+                documents: docs.into_iter().collect(),
+            }),
+    );
+    edges.truncate(MAX_GRAPH_EDGES);
+
+    PartyGraph {
+        nodes: nodes.into_values().collect(),
+        edges,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::StubLlm;
+
+    const CONTRACT: &str = "MASTER SERVICES AGREEMENT\n\n\
+        This Agreement is made between Acme Holdings Inc. and Globex, LLC \
+        (together, the Parties).\n\
+        WHEREAS Initech Corp. is an affiliate of Globex, LLC;\n\
+        Notices to Ms. Jane Okafor, General Counsel.\n\n\
+        By: John Q. Smith\n\
+        Name: Maria Delgado\n";
+
+    fn names(entities: &[ExtractedEntity]) -> Vec<&str> {
+        entities.iter().map(|e| e.name.as_str()).collect()
+    }
+
+    #[test]
+    fn heuristic_finds_organizations_and_people() {
+        let entities = extract_entities_heuristic(CONTRACT);
+        let found = names(&entities);
+        assert!(found.contains(&"Acme Holdings Inc."), "{found:?}");
+        assert!(found.contains(&"Globex, LLC"), "{found:?}");
+        assert!(found.contains(&"Initech Corp."), "{found:?}");
+        assert!(found.contains(&"Jane Okafor"), "{found:?}");
+        assert!(found.contains(&"John Q. Smith"), "{found:?}");
+        assert!(found.contains(&"Maria Delgado"), "{found:?}");
+
+        let globex = entities
+            .iter()
+            .filter(|e| e.name == "Globex, LLC")
+            .collect::<Vec<_>>();
+        assert_eq!(globex.len(), 1, "duplicates should merge");
+        assert_eq!(globex[0].entity_type, PartyEntityType::Organization);
+        assert!(
+            globex[0]
+                .snippet
+                .as_deref()
+                .is_some_and(|s| s.contains("Acme Holdings"))
+        );
+    }
+
+    #[test]
+    fn heuristic_ignores_bare_suffixes_and_single_names() {
+        let entities = extract_entities_heuristic("Limited liability applies. Dr. Who said so.");
+        assert!(entities.is_empty(), "{:?}", names(&entities));
+
+        let entities = extract_entities_heuristic("Payment is due to Hooli LLC.");
+        assert_eq!(names(&entities), vec!["Hooli LLC"]);
+    }
+
+    #[test]
+    fn parse_llm_entities_filters_unknown_types() {
+        let parsed = parse_llm_entities(
+            r#"Here: {"entities": [
+                {"name": "Umbrella Corp", "type": "company"},
+                {"name": "Alice Park", "type": "person", "confidence": 0.6},
+                {"name": "Ontario", "type": "place"}
+            ]}"#,
+            "Umbrella Corp will indemnify Alice Park.",
+        )
+        .expect("parsed");
+        assert_eq!(names(&parsed), vec!["Umbrella Corp", "Alice Park"]);
+        assert_eq!(parsed[0].entity_type, PartyEntityType::Organization);
+        assert_eq!(parsed[1].confidence, 0.6);
+        assert!(parsed[0].snippet.is_some());
+        assert!(parse_llm_entities("no json", "").is_none());
+    }
+
+    #[tokio::test]
+    async fn extract_entities_merges_llm_results() {
+        let llm = StubLlm::new(
+            r#"{"entities": [{"name": "Hooli", "type": "organization"},
+                             {"name": "Acme Holdings Inc.", "type": "organization", "confidence": 0.95}]}"#,
+        );
+        let entities = extract_entities(Some(&llm), "msa.md", CONTRACT).await;
+        let found = names(&entities);
+        assert!(found.contains(&"Hooli"));
+        assert!(found.contains(&"Maria Delgado"));
+        let acme = entities
+            .iter()
+            .find(|e| e.name == "Acme Holdings Inc.")
+            .expect("acme");
+        assert_eq!(acme.extractor, "llm");
+
+        let failing = StubLlm::failing("stub");
+        let entities = extract_entities(Some(&failing), "msa.md", CONTRACT).await;
+        assert!(entities.iter().all(|e| e.extractor == "heuristic"));
+    }
+
+    #[test]
+    fn document_entities_cover_dates_and_amounts() {
+        let content = "On March 3, 2023 Acme paid $1,250.50 and later US$ 2 million.\n\
+            A fee of EUR 500 was due 2023-03-03; another $1250.5 followed.\n";
+        let parties = extract_entities_heuristic(CONTRACT);
+        let rows = document_entities(content, &parties);
+
+        let of_kind = |kind: DocumentEntityKind| {
+            rows.iter()
+                .filter(|row| row.kind == kind)
+                .map(|row| row.normalized_value.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(of_kind(DocumentEntityKind::Date), vec!["2023-03-03"]);
+        assert_eq!(
+            of_kind(DocumentEntityKind::Amount),
+            vec!["$ 1250.50", "USD 2000000.00", "EUR 500.00"]
+        );
+        assert!(of_kind(DocumentEntityKind::Organization).contains(&"globex llc"));
+        assert!(of_kind(DocumentEntityKind::Person).contains(&"maria delgado"));
+        let amount = rows
+            .iter()
+            .find(|row| row.normalized_value == "USD 2000000.00")
+            .expect("amount");
+        assert_eq!(amount.value, "US$ 2 million");
+    }
+
+    #[test]
+    fn party_graph_links_parties_candidates_and_mentions() {
+        let now = chrono::Utc::now();
+        let party = MatterPartyRecord {
+            id: uuid::Uuid::new_v4(),
+            matter_id: "acme".to_string(),
+            party_id: uuid::Uuid::new_v4(),
+            name: "Acme Holdings Inc.".to_string(),
+            role: crate::db::PartyRole::Client,
+            aliases: vec!["Acme".to_string()],
+            notes: None,
+            opened_at: None,
+            closed_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let candidate = |name: &str, status: PartyCandidateStatus| PartyCandidateRecord {
+            id: uuid::Uuid::new_v4(),
+            matter_id: "acme".to_string(),
+            name: name.to_string(),
+            entity_type: PartyEntityType::Organization,
+            source_path: "matters/acme/msa.md".to_string(),
+            snippet: None,
+            confidence: 0.8,
+            extractor: "heuristic".to_string(),
+            status,
+            party_id: None,
+            reviewed_by: None,
+            reviewed_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let entity = |value: &str, path: &str| DocumentEntityRecord {
+            id: uuid::Uuid::new_v4(),
+            user_id: "default".to_string(),
+            matter_id: "acme".to_string(),
+            kind: DocumentEntityKind::Organization,
+            value: value.to_string(),
+            normalized_value: normalize_party_name(value),
+            source_path: path.to_string(),
+            snippet: None,
+            confidence: 0.8,
+            extractor: "heuristic".to_string(),
+            created_at: now,
+        };
+        let relationship = PartyRelationshipRecord {
+            id: uuid::Uuid::new_v4(),
+            parent_party_id: uuid::Uuid::new_v4(),
+            parent_name: "Acme Parent plc".to_string(),
+            child_party_id: party.party_id,
+            child_name: "Acme Holdings Inc.".to_string(),
+            kind: "parent_of".to_string(),
+            created_at: now,
+            updated_at: now,
+        };
+
+        let graph = build_party_graph(
+            std::slice::from_ref(&party),
+            &[relationship],
+            &[
+                candidate("Globex LLC", PartyCandidateStatus::Pending),
+                candidate("Hooli LLC", PartyCandidateStatus::Rejected),
+            ],
+            &[
+                entity("ACME", "matters/acme/msa.md"),
+                entity("Globex LLC", "matters/acme/msa.md"),
+                entity("Hooli LLC", "matters/acme/msa.md"),
+                entity("Initech Corp", "matters/acme/letter.md"),
+            ],
+        );
+
+        let status_of = |id: &str| {
+            graph
+                .nodes
+                .iter()
+                .find(|node| node.id == id)
+                .map(|node| node.status)
+        };
+        assert_eq!(status_of("acme holdings inc"), Some("party"));
+        assert_eq!(status_of("globex llc"), Some("candidate"));
+        assert_eq!(status_of("initech corp"), Some("mentioned"));
+        assert_eq!(status_of("acme parent plc"), Some("related"));
+        assert_eq!(status_of("hooli llc"), None, "rejected names stay out");
+        assert_eq!(status_of("acme"), None, "aliases resolve to their party");
+
+        assert!(graph.edges.iter().any(|edge| edge.kind == "parent_of"
+            && edge.source == "acme parent plc"
+            && edge.target == "acme holdings inc"));
+        let co_mention = graph
+            .edges
+            .iter()
+            .find(|edge| edge.kind == "mentioned_with")
+            .expect("co-mention edge");
+        assert_eq!(
+            (co_mention.source.as_str(), co_mention.target.as_str()),
+            ("acme holdings inc", "globex llc")
+        );
+        assert_eq!(co_mention.documents, vec!["matters/acme/msa.md"]);
+        assert_eq!(graph.edges.len(), 2);
+    }
+}
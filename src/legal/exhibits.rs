@@ -0,0 +1,201 @@
+//! Exhibit register output: party prefixes, stickers, cover sheets, and the
+//! exhibit list.
+//!
+//! Exhibits are numbered per party prefix in the order they are marked
+//! (`P-1`, `P-2`, `D-1`); the store assigns the number and never reuses it,
+//! so a withdrawn exhibit keeps its label and regenerated exhibit lists and
+//! filing packages number every exhibit the same way.
+
+use chrono::{DateTime, Utc};
+
+use crate::db::{ExhibitStatus, MatterExhibitRecord};
+use crate::legal::markdown::table_cell;
+
+/// Prefix used when a request does not name one.
+pub const DEFAULT_PARTY_PREFIX: &str = "P";
+
+const MAX_PREFIX_CHARS: usize = 8;
+const STICKER_WIDTH: usize = 30;
+
+/// Uppercased party prefix, or why it is not usable. Prefixes are 1-8 ASCII
+/// letters so labels stay unambiguous (`PX-4`, not `P-X-4`).
+pub fn normalize_party_prefix(raw: &str) -> Result<String, String> {
+    let prefix = raw.trim().to_ascii_uppercase();
+    if prefix.is_empty()
+        || prefix.len() > MAX_PREFIX_CHARS
+        || !prefix.chars().all(|c| c.is_ascii_alphabetic())
+    {
+        return Err(format!(
+            "party prefix must be 1-{MAX_PREFIX_CHARS} letters (e.g. 'P', 'D', 'J')"
+        ));
+    }
+    Ok(prefix)
+}
+
+/// Whose exhibit a prefix marks, for stickers.
+pub fn party_title(prefix: &str) -> String {
+    match prefix {
+        "P" => "PLAINTIFF'S EXHIBIT".to_string(),
+        "D" => "DEFENDANT'S EXHIBIT".to_string(),
+        "J" | "JX" => "JOINT EXHIBIT".to_string(),
+        "A" => "APPLICANT'S EXHIBIT".to_string(),
+        "R" => "RESPONDENT'S EXHIBIT".to_string(),
+        "G" | "GX" => "GOVERNMENT EXHIBIT".to_string(),
+        _ => "EXHIBIT".to_string(),
+    }
+}
+
+pub fn exhibit_list_path(matter_prefix: &str) -> String {
+    format!("{matter_prefix}/exhibits/exhibit_list.md")
+}
+
+/// Cover sheet path, named so pre-filing validation reads its label.
+pub fn cover_sheet_path(matter_prefix: &str, label: &str) -> String {
+    format!(
+        "{matter_prefix}/exhibits/covers/exhibit_{}.md",
+        label.to_ascii_lowercase()
+    )
+}
+
+/// Boxed exhibit sticker.
+pub fn render_sticker(exhibit: &MatterExhibitRecord, case_number: Option<&str>) -> String {
+    let mut rows = vec![party_title(&exhibit.party_prefix), exhibit.label.clone()];
+    if let Some(case_number) = case_number {
+        rows.push(format!("Case No. {case_number}"));
+    }
+    let width = rows
+        .iter()
+        .map(|row| row.chars().count() + 4)
+        .max()
+        .unwrap_or_default()
+        .max(STICKER_WIDTH);
+    let border = format!("+{}+", "-".repeat(width));
+    let mut lines = vec![border.clone()];
+    lines.extend(rows.iter().map(|row| format!("|{row:^width$}|")));
+    lines.push(border);
+    lines.join("\n")
+}
+
+/// Cover sheet for an exhibit: the case caption (if known), the sticker,
+/// and what the exhibit is.
+pub fn render_cover_sheet(
+    exhibit: &MatterExhibitRecord,
+    caption: Option<&str>,
+    case_number: Option<&str>,
+) -> String {
+    let mut out = String::new();
+    if let Some(caption) = caption {
+        out.push_str(&format!("```text\n{caption}\n```\n\n"));
+    }
+    out.push_str(&format!("# Exhibit {}\n\n", exhibit.label));
+    out.push_str(&format!(
+        "```text\n{}\n```\n\n",
+        render_sticker(exhibit, case_number)
+    ));
+    if let Some(description) = &exhibit.description {
+        out.push_str(&format!("{description}\n\n"));
+    }
+    out.push_str(&format!("- Document: `{}`\n", exhibit.path));
+    out.push_str(&format!(
+        "- Marked: {} by {}\n",
+        exhibit.created_at.date_naive(),
+        exhibit.created_by
+    ));
+    if let Some(withdrawn_at) = exhibit.withdrawn_at {
+        out.push_str(&format!("- Withdrawn: {}\n", withdrawn_at.date_naive()));
+    }
+    out
+}
+
+/// Exhibit list document. Withdrawn exhibits stay listed so the numbering
+/// the court has seen does not change.
+pub fn render_exhibit_list(
+    matter_id: &str,
+    exhibits: &[MatterExhibitRecord],
+    generated_at: DateTime<Utc>,
+) -> String {
+    let mut out = format!(
+        "# Exhibit List\n\nMatter: `{matter_id}`\nGenerated: {}\n\n",
+        generated_at.to_rfc3339()
+    );
+    if exhibits.is_empty() {
+        out.push_str("- No exhibits marked.\n");
+        return out;
+    }
+    out.push_str("| Exhibit | Description | Document | Marked | Status |\n");
+    out.push_str("|---|---|---|---|---|\n");
+    for exhibit in exhibits {
+        let status = match (exhibit.status, exhibit.withdrawn_at) {
+            (ExhibitStatus::Withdrawn, Some(at)) => format!("Withdrawn {}", at.date_naive()),
+            (ExhibitStatus::Withdrawn, None) => "Withdrawn".to_string(),
+            (ExhibitStatus::Marked, _) => "Marked".to_string(),
+        };
+        out.push_str(&format!(
+            "| {} | {} | `{}` | {} | {} |\n",
+            exhibit.label,
+            table_cell(exhibit.description.as_deref().unwrap_or("")),
+            table_cell(&exhibit.path),
+            exhibit.created_at.date_naive(),
+            status
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn exhibit(prefix: &str, sequence: i32, status: ExhibitStatus) -> MatterExhibitRecord {
+        MatterExhibitRecord {
+            id: Uuid::new_v4(),
+            user_id: "default".to_string(),
+            matter_id: "demo".to_string(),
+            party_prefix: prefix.to_string(),
+            sequence,
+            label: format!("{prefix}-{sequence}"),
+            path: format!("matters/demo/evidence/doc{sequence}.md"),
+            matter_document_id: None,
+            description: Some("Lease | signed copy".to_string()),
+            status,
+            created_by: "default".to_string(),
+            created_at: Utc::now(),
+            withdrawn_at: (status == ExhibitStatus::Withdrawn).then(Utc::now),
+        }
+    }
+
+    #[test]
+    fn party_prefixes_are_normalized() {
+        assert_eq!(normalize_party_prefix(" d ").unwrap(), "D");
+        assert_eq!(normalize_party_prefix("px").unwrap(), "PX");
+        assert!(normalize_party_prefix("").is_err());
+        assert!(normalize_party_prefix("P-1").is_err());
+        assert!(normalize_party_prefix("PLAINTIFFS").is_err());
+    }
+
+    #[test]
+    fn sticker_and_list_render_labels() {
+        let sticker = render_sticker(&exhibit("D", 2, ExhibitStatus::Marked), Some("CV-24-0012"));
+        let lines: Vec<&str> = sticker.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].contains("DEFENDANT'S EXHIBIT"));
+        assert_eq!(lines[2].trim_matches(|c| c == '|' || c == ' '), "D-2");
+        assert!(lines.iter().all(|line| line.chars().count() == 32));
+
+        let list = render_exhibit_list(
+            "demo",
+            &[
+                exhibit("P", 1, ExhibitStatus::Marked),
+                exhibit("P", 2, ExhibitStatus::Withdrawn),
+            ],
+            Utc::now(),
+        );
+        assert!(list.contains("| P-1 | Lease \\| signed copy |"));
+        assert!(list.contains("| Withdrawn "));
+        assert_eq!(
+            cover_sheet_path("matters/demo", "P-12"),
+            "matters/demo/exhibits/covers/exhibit_p-12.md"
+        );
+    }
+}
@@ -0,0 +1,661 @@
+//! Pre-filing validation of a matter's filing package.
+//!
+//! Jurisdiction rule sets (bundled in `filing_rules.toml`) set page limits,
+//! minimum font size and margins, required sections per document type, and
+//! what counts as proof of service. Documents are workspace markdown, so
+//! page counts are estimated from word count and font/margin checks read the
+//! export metadata in a document's front matter (`font_size` in points,
+//! `margins` in inches) rather than a rendered PDF.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::legal::caption::caption_profile;
+
+/// Rule set used when no other rule set matches.
+pub const DEFAULT_RULESET_ID: &str = "default";
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FilingRuleset {
+    pub id: String,
+    pub label: String,
+    #[serde(default)]
+    pub jurisdictions: Vec<String>,
+    #[serde(default)]
+    pub caption_styles: Vec<String>,
+    pub max_pages: u32,
+    pub words_per_page: u32,
+    pub min_font_pt: f64,
+    pub min_margin_in: f64,
+    #[serde(default)]
+    pub service_proof: Vec<String>,
+    #[serde(default)]
+    pub sections: Vec<SectionRule>,
+}
+
+/// Headings required in documents whose file name contains one of
+/// `documents` (e.g. every `*brief*` needs an Argument section).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SectionRule {
+    pub documents: Vec<String>,
+    pub required: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FilingRulesConfig {
+    rulesets: Vec<FilingRuleset>,
+}
+
+static FILING_RULESETS: LazyLock<Result<Vec<FilingRuleset>, String>> =
+    LazyLock::new(|| parse_rulesets(include_str!("filing_rules.toml")));
+
+static EXHIBIT_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?i:exhibit)\s+(?:([A-Z]{1,8})-)?([A-Z]|\d{1,3})\b")
+        .expect("valid exhibit reference regex")
+});
+static EXHIBIT_FILE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^exhibit[\s_-]*(?:([a-z]{1,8})-)?([a-z]|\d{1,3})(?:[\s_.-]|$)")
+        .expect("valid exhibit file regex")
+});
+
+fn parse_rulesets(raw: &str) -> Result<Vec<FilingRuleset>, String> {
+    let parsed: FilingRulesConfig =
+        toml::from_str(raw).map_err(|e| format!("invalid filing rules TOML: {}", e))?;
+    if !parsed
+        .rulesets
+        .iter()
+        .any(|ruleset| ruleset.id == DEFAULT_RULESET_ID)
+    {
+        return Err(format!(
+            "filing rules must include a '{DEFAULT_RULESET_ID}' rule set"
+        ));
+    }
+    if let Some(bad) = parsed
+        .rulesets
+        .iter()
+        .find(|ruleset| ruleset.max_pages == 0 || ruleset.words_per_page == 0)
+    {
+        return Err(format!(
+            "rule set '{}' needs a non-zero max_pages and words_per_page",
+            bad.id
+        ));
+    }
+    Ok(parsed.rulesets)
+}
+
+pub fn filing_rulesets() -> Result<&'static [FilingRuleset], String> {
+    match &*FILING_RULESETS {
+        Ok(rulesets) => Ok(rulesets.as_slice()),
+        Err(err) => Err(err.clone()),
+    }
+}
+
+/// Rule set for a matter's jurisdiction: an exact jurisdiction match, then
+/// the jurisdiction's caption style, then the default.
+pub fn ruleset_for_jurisdiction(
+    jurisdiction: Option<&str>,
+) -> Result<&'static FilingRuleset, String> {
+    let rulesets = filing_rulesets()?;
+    let code: String = jurisdiction
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect();
+    let style = caption_profile(jurisdiction, None).style;
+    rulesets
+        .iter()
+        .find(|ruleset| {
+            !code.is_empty()
+                && ruleset
+                    .jurisdictions
+                    .iter()
+                    .any(|j| j.eq_ignore_ascii_case(&code))
+        })
+        .or_else(|| {
+            rulesets
+                .iter()
+                .find(|ruleset| ruleset.caption_styles.iter().any(|s| s == style.as_str()))
+        })
+        .or_else(|| {
+            rulesets
+                .iter()
+                .find(|ruleset| ruleset.id == DEFAULT_RULESET_ID)
+        })
+        .ok_or_else(|| format!("no '{DEFAULT_RULESET_ID}' filing rule set"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FilingIssue {
+    /// `page_limit`, `format_metadata`, `required_sections`,
+    /// `certificate_of_service`, or `exhibit_numbering`.
+    pub check: &'static str,
+    pub severity: IssueSeverity,
+    pub message: String,
+}
+
+impl FilingIssue {
+    fn error(check: &'static str, message: String) -> Self {
+        Self {
+            check,
+            severity: IssueSeverity::Error,
+            message,
+        }
+    }
+
+    fn warning(check: &'static str, message: String) -> Self {
+        Self {
+            check,
+            severity: IssueSeverity::Warning,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DocumentValidation {
+    pub path: String,
+    pub words: usize,
+    pub estimated_pages: u32,
+    pub issues: Vec<FilingIssue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FilingValidationReport {
+    pub ruleset_id: String,
+    pub ruleset_label: String,
+    /// True when no check reported an error; warnings do not fail a package.
+    pub passed: bool,
+    pub documents: Vec<DocumentValidation>,
+    pub package_issues: Vec<FilingIssue>,
+}
+
+/// A document in the package: its workspace path and markdown content.
+#[derive(Debug, Clone, Copy)]
+pub struct FilingDocument<'a> {
+    pub path: &'a str,
+    pub content: &'a str,
+}
+
+/// Export metadata read from a document's YAML front matter.
+#[derive(Debug, Default, Deserialize)]
+struct FormatMetadata {
+    #[serde(default)]
+    font_size: Option<f64>,
+    #[serde(default, alias = "margin")]
+    margins: Option<f64>,
+}
+
+/// Split `---`-delimited front matter from the body.
+fn split_front_matter(content: &str) -> (Option<&str>, &str) {
+    let Some(rest) = content.strip_prefix("---\n") else {
+        return (None, content);
+    };
+    match rest.find("\n---") {
+        Some(end) => {
+            let body = &rest[end + 4..];
+            (Some(&rest[..end]), body.strip_prefix('\n').unwrap_or(body))
+        }
+        None => (None, content),
+    }
+}
+
+/// Heading text of markdown `#` lines and all-caps lines (how captions and
+/// briefs usually set headings), lowercased.
+fn headings(body: &str) -> Vec<String> {
+    body.lines()
+        .filter_map(|line| {
+            let trimmed = line.trim();
+            let text = trimmed
+                .trim_start_matches('#')
+                .trim()
+                .trim_matches('*')
+                .trim();
+            let is_markdown = trimmed.starts_with('#');
+            let is_caps = text.len() <= 80
+                && text.chars().any(char::is_alphabetic)
+                && !text.chars().any(char::is_lowercase);
+            (!text.is_empty() && (is_markdown || is_caps)).then(|| text.to_lowercase())
+        })
+        .collect()
+}
+
+fn file_name(path: &str) -> String {
+    path.rsplit('/').next().unwrap_or(path).to_lowercase()
+}
+
+fn exhibit_label(captures: &regex::Captures<'_>) -> String {
+    match captures.get(1) {
+        Some(prefix) => format!("{}-{}", prefix.as_str(), &captures[2]),
+        None => captures[2].to_string(),
+    }
+    .to_ascii_uppercase()
+}
+
+/// Exhibit label named by a file such as `exhibit_a.md` or
+/// `exhibit_p-3.md`.
+pub fn exhibit_label_from_path(path: &str) -> Option<String> {
+    EXHIBIT_FILE_RE
+        .captures(&file_name(path))
+        .map(|captures| exhibit_label(&captures))
+}
+
+fn validate_document(ruleset: &FilingRuleset, doc: FilingDocument<'_>) -> DocumentValidation {
+    let (front_matter, body) = split_front_matter(doc.content);
+    let words = body.split_whitespace().count();
+    let estimated_pages = words.div_ceil(ruleset.words_per_page as usize) as u32;
+    let mut issues = Vec::new();
+
+    if estimated_pages > ruleset.max_pages {
+        issues.push(FilingIssue::error(
+            "page_limit",
+            format!(
+                "About {estimated_pages} pages ({words} words) exceeds the {}-page limit",
+                ruleset.max_pages
+            ),
+        ));
+    }
+
+    let format = front_matter
+        .and_then(|raw| serde_yml::from_str::<FormatMetadata>(raw).ok())
+        .unwrap_or_default();
+    match format.font_size {
+        Some(size) if size < ruleset.min_font_pt => issues.push(FilingIssue::error(
+            "format_metadata",
+            format!(
+                "Font size {size}pt is below the {}pt minimum",
+                ruleset.min_font_pt
+            ),
+        )),
+        Some(_) => {}
+        None => issues.push(FilingIssue::warning(
+            "format_metadata",
+            "No font_size in export metadata; check the exported PDF".to_string(),
+        )),
+    }
+    match format.margins {
+        Some(margin) if margin < ruleset.min_margin_in => issues.push(FilingIssue::error(
+            "format_metadata",
+            format!(
+                "Margins of {margin}in are below the {}in minimum",
+                ruleset.min_margin_in
+            ),
+        )),
+        Some(_) => {}
+        None => issues.push(FilingIssue::warning(
+            "format_metadata",
+            "No margins in export metadata; check the exported PDF".to_string(),
+        )),
+    }
+
+    let name = file_name(doc.path);
+    let doc_headings = headings(body);
+    for rule in ruleset.sections.iter().filter(|rule| {
+        rule.documents
+            .iter()
+            .any(|kind| name.contains(kind.as_str()))
+    }) {
+        let missing: Vec<&str> = rule
+            .required
+            .iter()
+            .filter(|section| {
+                !doc_headings
+                    .iter()
+                    .any(|heading| heading.contains(section.as_str()))
+            })
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            issues.push(FilingIssue::error(
+                "required_sections",
+                format!("Missing required sections: {}", missing.join(", ")),
+            ));
+        }
+    }
+
+    DocumentValidation {
+        path: doc.path.to_string(),
+        words,
+        estimated_pages,
+        issues,
+    }
+}
+
+/// Labels missing from each exhibit series. Series are keyed by party
+/// prefix (`P-1`, `P-2`, `D-1`); within a series numbers run from 1 and
+/// letters from A.
+fn exhibit_gaps(labels: &BTreeSet<String>) -> Result<Vec<String>, String> {
+    let mut series: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for label in labels {
+        let (prefix, item) = label.split_once('-').unwrap_or(("", label.as_str()));
+        series.entry(prefix).or_default().push(item);
+    }
+    let mut gaps = Vec::new();
+    for (prefix, items) in series {
+        let numbers: Vec<u32> = items.iter().filter_map(|i| i.parse().ok()).collect();
+        let letters: Vec<char> = items
+            .iter()
+            .filter(|i| i.parse::<u32>().is_err())
+            .filter_map(|i| i.chars().next())
+            .collect();
+        if !numbers.is_empty() && !letters.is_empty() {
+            return Err("Exhibits mix numbers and letters".to_string());
+        }
+        let missing: Vec<String> = if let Some(&max) = numbers.iter().max() {
+            (1..=max)
+                .filter(|n| !numbers.contains(n))
+                .map(|n| n.to_string())
+                .collect()
+        } else if let Some(&max) = letters.iter().max() {
+            ('A'..=max)
+                .filter(|c| !letters.contains(c))
+                .map(|c| c.to_string())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        gaps.extend(missing.into_iter().map(|item| {
+            if prefix.is_empty() {
+                item
+            } else {
+                format!("{prefix}-{item}")
+            }
+        }));
+    }
+    Ok(gaps)
+}
+
+fn package_issues(
+    ruleset: &FilingRuleset,
+    docs: &[FilingDocument<'_>],
+    filed_exhibits: &[String],
+) -> Vec<FilingIssue> {
+    let mut issues = Vec::new();
+
+    let has_service_proof = docs.iter().any(|doc| {
+        headings(split_front_matter(doc.content).1)
+            .iter()
+            .any(|heading| {
+                ruleset
+                    .service_proof
+                    .iter()
+                    .any(|proof| heading.starts_with(proof.as_str()))
+            })
+    });
+    if !ruleset.service_proof.is_empty() && !has_service_proof {
+        issues.push(FilingIssue::error(
+            "certificate_of_service",
+            format!(
+                "No proof of service in the package (expected a heading: {})",
+                ruleset.service_proof.join(", ")
+            ),
+        ));
+    }
+
+    let mut referenced = BTreeSet::new();
+    let mut filed: BTreeSet<String> = filed_exhibits
+        .iter()
+        .map(|label| label.to_ascii_uppercase())
+        .collect();
+    for doc in docs {
+        for captures in EXHIBIT_REF_RE.captures_iter(doc.content) {
+            referenced.insert(exhibit_label(&captures));
+        }
+        filed.extend(exhibit_label_from_path(doc.path));
+    }
+    let all: BTreeSet<String> = referenced.union(&filed).cloned().collect();
+    match exhibit_gaps(&all) {
+        Err(message) => issues.push(FilingIssue::error("exhibit_numbering", message)),
+        Ok(gaps) if !gaps.is_empty() => issues.push(FilingIssue::error(
+            "exhibit_numbering",
+            format!("Exhibit sequence skips {}", gaps.join(", ")),
+        )),
+        Ok(_) => {}
+    }
+    if !filed.is_empty() {
+        let missing: Vec<&str> = referenced.difference(&filed).map(String::as_str).collect();
+        if !missing.is_empty() {
+            issues.push(FilingIssue::warning(
+                "exhibit_numbering",
+                format!(
+                    "Referenced exhibits without an exhibit file: {}",
+                    missing.join(", ")
+                ),
+            ));
+        }
+    }
+    issues
+}
+
+/// Validate the package's filing documents. `filed_exhibits` are the labels
+/// of exhibits on file outside those documents (the exhibit register and
+/// `exhibit_*` file names).
+pub fn validate_filing_package(
+    ruleset: &FilingRuleset,
+    docs: &[FilingDocument<'_>],
+    filed_exhibits: &[String],
+) -> FilingValidationReport {
+    let documents: Vec<DocumentValidation> = docs
+        .iter()
+        .map(|doc| validate_document(ruleset, *doc))
+        .collect();
+    let package_issues = package_issues(ruleset, docs, filed_exhibits);
+    let passed = documents
+        .iter()
+        .flat_map(|doc| doc.issues.iter())
+        .chain(package_issues.iter())
+        .all(|issue| issue.severity != IssueSeverity::Error);
+    FilingValidationReport {
+        ruleset_id: ruleset.id.clone(),
+        ruleset_label: ruleset.label.clone(),
+        passed,
+        documents,
+        package_issues,
+    }
+}
+
+/// Markdown section summarizing a validation report.
+pub fn render_validation_report(report: &FilingValidationReport) -> String {
+    let issue_line = |issue: &FilingIssue| {
+        format!(
+            "- {} `{}`: {}\n",
+            match issue.severity {
+                IssueSeverity::Error => "**Error**",
+                IssueSeverity::Warning => "Warning",
+            },
+            issue.check,
+            issue.message
+        )
+    };
+    let mut out = format!(
+        "## Pre-filing Validation\n\nRules: {} (`{}`)\nResult: {}\n\n",
+        report.ruleset_label,
+        report.ruleset_id,
+        if report.passed { "passed" } else { "failed" }
+    );
+    if report.documents.is_empty() {
+        out.push_str("- No pleadings or filings to validate.\n");
+    }
+    for doc in &report.documents {
+        out.push_str(&format!(
+            "### `{}`\n\nAbout {} pages ({} words)\n\n",
+            doc.path, doc.estimated_pages, doc.words
+        ));
+        if doc.issues.is_empty() {
+            out.push_str("- No issues.\n");
+        }
+        for issue in &doc.issues {
+            out.push_str(&issue_line(issue));
+        }
+        out.push('\n');
+    }
+    if !report.package_issues.is_empty() {
+        out.push_str("### Package\n\n");
+        for issue in &report.package_issues {
+            out.push_str(&issue_line(issue));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn formatted(body: &str) -> String {
+        format!("---\nfont_size: 12\nmargins: 1\n---\n{body}")
+    }
+
+    #[test]
+    fn bundled_rules_resolve_by_jurisdiction_and_style() {
+        assert_eq!(ruleset_for_jurisdiction(Some("SDNY")).unwrap().id, "sdny");
+        assert_eq!(
+            ruleset_for_jurisdiction(Some("EDNY")).unwrap().id,
+            "us_federal"
+        );
+        assert_eq!(ruleset_for_jurisdiction(Some("NY")).unwrap().id, "us_state");
+        assert_eq!(ruleset_for_jurisdiction(Some("ON")).unwrap().id, "ontario");
+        assert_eq!(ruleset_for_jurisdiction(None).unwrap().id, "default");
+    }
+
+    #[test]
+    fn documents_are_checked_for_pages_format_and_sections() {
+        let ruleset = ruleset_for_jurisdiction(Some("EDNY")).unwrap();
+        let long = formatted(&format!(
+            "# Argument\n\n{}\n\n# Conclusion\n",
+            "word ".repeat(280 * 26)
+        ));
+        let small_font = "---\nfont_size: 10.5\nmargin: 0.75\n---\n# ARGUMENT\n";
+        let unformatted = "ARGUMENT\n\nText.\n";
+        let docs = [
+            FilingDocument {
+                path: "matters/a/drafts/opposition_brief.md",
+                content: &long,
+            },
+            FilingDocument {
+                path: "matters/a/drafts/reply_brief.md",
+                content: small_font,
+            },
+            FilingDocument {
+                path: "matters/a/drafts/notice.md",
+                content: unformatted,
+            },
+        ];
+        let report = validate_filing_package(ruleset, &docs, &[]);
+        assert!(!report.passed);
+
+        let checks = |i: usize| -> Vec<(&str, IssueSeverity)> {
+            report.documents[i]
+                .issues
+                .iter()
+                .map(|issue| (issue.check, issue.severity))
+                .collect()
+        };
+        assert_eq!(report.documents[0].estimated_pages, 27);
+        assert_eq!(checks(0), vec![("page_limit", IssueSeverity::Error)]);
+        assert_eq!(
+            checks(1),
+            vec![
+                ("format_metadata", IssueSeverity::Error),
+                ("format_metadata", IssueSeverity::Error),
+                ("required_sections", IssueSeverity::Error),
+            ]
+        );
+        assert!(
+            report.documents[1].issues[2]
+                .message
+                .ends_with("conclusion")
+        );
+        assert_eq!(
+            checks(2),
+            vec![
+                ("format_metadata", IssueSeverity::Warning),
+                ("format_metadata", IssueSeverity::Warning),
+            ]
+        );
+    }
+
+    #[test]
+    fn package_needs_service_proof_and_continuous_exhibits() {
+        let ruleset = ruleset_for_jurisdiction(Some("ON")).unwrap();
+        let motion = formatted("Relies on Exhibit A and Exhibit C.\n");
+        let report = validate_filing_package(
+            ruleset,
+            &[FilingDocument {
+                path: "matters/a/drafts/motion_record.md",
+                content: &motion,
+            }],
+            &[exhibit_label_from_path("matters/a/exhibits/exhibit_a.md").unwrap()],
+        );
+        let messages: Vec<&str> = report
+            .package_issues
+            .iter()
+            .map(|issue| issue.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "No proof of service in the package (expected a heading: affidavit of service, certificate of service)",
+                "Exhibit sequence skips B",
+                "Referenced exhibits without an exhibit file: C",
+            ]
+        );
+
+        let served = formatted("AFFIDAVIT OF SERVICE\n\nI served Exhibit 1 and Exhibit 2.\n");
+        let report = validate_filing_package(
+            ruleset,
+            &[FilingDocument {
+                path: "matters/a/drafts/service.md",
+                content: &served,
+            }],
+            &[],
+        );
+        assert!(report.passed, "{:?}", report);
+
+        let mixed = formatted("CERTIFICATE OF SERVICE\n\nSee Exhibit 1 and Exhibit B.\n");
+        let report = validate_filing_package(
+            ruleset,
+            &[FilingDocument {
+                path: "matters/a/drafts/mixed.md",
+                content: &mixed,
+            }],
+            &[],
+        );
+        assert_eq!(
+            report.package_issues[0].message,
+            "Exhibits mix numbers and letters"
+        );
+
+        let prefixed =
+            formatted("CERTIFICATE OF SERVICE\n\nSee Exhibit P-1, Exhibit P-3 and Exhibit D-1.\n");
+        let report = validate_filing_package(
+            ruleset,
+            &[FilingDocument {
+                path: "matters/a/drafts/prefixed.md",
+                content: &prefixed,
+            }],
+            &["P-1".to_string(), "D-1".to_string(), "P-3".to_string()],
+        );
+        assert_eq!(
+            report.package_issues[0].message,
+            "Exhibit sequence skips P-2"
+        );
+        assert_eq!(
+            exhibit_label_from_path("matters/a/exhibits/covers/exhibit_p-12.md").as_deref(),
+            Some("P-12")
+        );
+        assert_eq!(
+            exhibit_label_from_path("matters/a/exhibits/exhibit_list.md"),
+            None
+        );
+    }
+}
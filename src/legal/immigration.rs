@@ -0,0 +1,507 @@
+//! Immigration forms: field sets for USCIS forms, filled from matter data.
+//!
+//! Form definitions live in the bundled `immigration_forms.toml`. Each field
+//! names the data keys it is read from; values come from the matter's
+//! `immigration/case_data.yaml` (written by the immigration scaffold), the
+//! matter metadata, the client record, and the matter's custom fields.
+//! [`fill_form`] normalizes each value to the form's format (dates as
+//! `MM/DD/YYYY`, A-Numbers as `A` plus nine digits), flags invalid values,
+//! and reports which required fields are still missing. Filled forms export
+//! as JSON or as XFDF, the field-data overlay PDF readers merge into the
+//! official fillable PDF.
+
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Matter-relative path of the case data file.
+pub const CASE_DATA_FILE: &str = "immigration/case_data.yaml";
+
+pub fn case_data_path(matter_prefix: &str) -> String {
+    format!("{matter_prefix}/{CASE_DATA_FILE}")
+}
+
+/// Export path for a filled form; `extension` is `json` or `xfdf`.
+pub fn form_export_path(matter_prefix: &str, form_id: &str, extension: &str) -> String {
+    format!("{matter_prefix}/immigration/forms/{form_id}.{extension}")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FormFieldKind {
+    Text,
+    Date,
+    ANumber,
+    Ssn,
+    ReceiptNumber,
+    Phone,
+    Email,
+    Choice,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormField {
+    pub id: String,
+    pub label: String,
+    pub sources: Vec<String>,
+    pub kind: FormFieldKind,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pdf_field: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImmigrationForm {
+    pub id: String,
+    pub title: String,
+    pub agency: String,
+    pub edition: String,
+    /// Rule ids in `court_rules.toml` to docket once the form is filed.
+    #[serde(default)]
+    pub deadline_presets: Vec<String>,
+    pub fields: Vec<FormField>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImmigrationFormsConfig {
+    forms: Vec<ImmigrationForm>,
+}
+
+static IMMIGRATION_FORMS: LazyLock<Result<Vec<ImmigrationForm>, String>> =
+    LazyLock::new(|| parse_forms(include_str!("immigration_forms.toml")));
+
+fn parse_forms(raw: &str) -> Result<Vec<ImmigrationForm>, String> {
+    let parsed: ImmigrationFormsConfig =
+        toml::from_str(raw).map_err(|e| format!("invalid immigration forms TOML: {e}"))?;
+    for form in &parsed.forms {
+        for field in &form.fields {
+            if field.sources.is_empty() {
+                return Err(format!(
+                    "field '{}' on form '{}' has no sources",
+                    field.id, form.id
+                ));
+            }
+            if field.kind == FormFieldKind::Choice && field.options.is_empty() {
+                return Err(format!(
+                    "choice field '{}' on form '{}' has no options",
+                    field.id, form.id
+                ));
+            }
+        }
+    }
+    Ok(parsed.forms)
+}
+
+pub fn immigration_forms() -> Result<&'static [ImmigrationForm], String> {
+    match &*IMMIGRATION_FORMS {
+        Ok(forms) => Ok(forms.as_slice()),
+        Err(err) => Err(err.clone()),
+    }
+}
+
+fn normalize_form_id(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Look up a form by id, ignoring case and punctuation (`I-130`, `i130`).
+pub fn get_immigration_form(form_id: &str) -> Result<Option<&'static ImmigrationForm>, String> {
+    let wanted = normalize_form_id(form_id);
+    Ok(immigration_forms()?
+        .iter()
+        .find(|form| normalize_form_id(&form.id) == wanted))
+}
+
+fn flatten_yaml(prefix: &str, value: &serde_yml::Value, out: &mut BTreeMap<String, String>) {
+    let scalar = match value {
+        serde_yml::Value::Null => return,
+        serde_yml::Value::Bool(value) => value.to_string(),
+        serde_yml::Value::Number(value) => value.to_string(),
+        serde_yml::Value::String(value) => value.clone(),
+        serde_yml::Value::Sequence(items) => items
+            .iter()
+            .filter_map(|item| match item {
+                serde_yml::Value::String(value) => Some(value.clone()),
+                serde_yml::Value::Number(value) => Some(value.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+        serde_yml::Value::Mapping(map) => {
+            for (key, child) in map {
+                let Some(key) = key.as_str() else {
+                    continue;
+                };
+                let key = if prefix.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten_yaml(&key, child, out);
+            }
+            return;
+        }
+        serde_yml::Value::Tagged(tagged) => return flatten_yaml(prefix, &tagged.value, out),
+    };
+    if !prefix.is_empty() && !scalar.trim().is_empty() {
+        out.insert(prefix.to_string(), scalar.trim().to_string());
+    }
+}
+
+/// Flatten case data YAML into dotted keys (`petitioner.family_name`).
+pub fn parse_case_data(raw: &str) -> Result<BTreeMap<String, String>, String> {
+    let value: serde_yml::Value =
+        serde_yml::from_str(raw).map_err(|e| format!("invalid case data YAML: {e}"))?;
+    let mut out = BTreeMap::new();
+    flatten_yaml("", &value, &mut out);
+    Ok(out)
+}
+
+/// Flatten a JSON object into `prefix.key` entries (for matter custom
+/// fields).
+pub fn flatten_json(prefix: &str, value: &serde_json::Value, out: &mut BTreeMap<String, String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map {
+                flatten_json(&format!("{prefix}.{key}"), child, out);
+            }
+        }
+        serde_json::Value::String(value) if !value.trim().is_empty() => {
+            out.insert(prefix.to_string(), value.trim().to_string());
+        }
+        serde_json::Value::Number(value) => {
+            out.insert(prefix.to_string(), value.to_string());
+        }
+        serde_json::Value::Bool(value) => {
+            out.insert(prefix.to_string(), value.to_string());
+        }
+        _ => {}
+    }
+}
+
+/// Normalize a raw value to the format the form expects.
+pub fn normalize_field_value(field: &FormField, raw: &str) -> Result<String, String> {
+    let raw = raw.trim();
+    let digits: String = raw.chars().filter(|c| c.is_ascii_digit()).collect();
+    match field.kind {
+        FormFieldKind::Text => Ok(raw.to_string()),
+        FormFieldKind::Date => ["%Y-%m-%d", "%m/%d/%Y"]
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(raw, format).ok())
+            .map(|date| date.format("%m/%d/%Y").to_string())
+            .ok_or_else(|| format!("'{raw}' is not a date (use YYYY-MM-DD)")),
+        FormFieldKind::ANumber => {
+            let rest = raw.trim_start_matches(['A', 'a']);
+            if rest
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == '-' || c == ' ')
+                && (7..=9).contains(&digits.len())
+            {
+                Ok(format!("A{digits:0>9}"))
+            } else {
+                Err(format!(
+                    "'{raw}' is not an A-Number (A followed by 7-9 digits)"
+                ))
+            }
+        }
+        FormFieldKind::Ssn => {
+            if digits.len() == 9
+                && raw
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || c == '-' || c == ' ')
+            {
+                Ok(format!(
+                    "{}-{}-{}",
+                    &digits[..3],
+                    &digits[3..5],
+                    &digits[5..]
+                ))
+            } else {
+                Err("Social Security numbers have 9 digits".to_string())
+            }
+        }
+        FormFieldKind::ReceiptNumber => {
+            let compact: String = raw
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric())
+                .collect::<String>()
+                .to_ascii_uppercase();
+            let (prefix, number) = compact.split_at(compact.len().min(3));
+            if prefix.len() == 3
+                && prefix.chars().all(|c| c.is_ascii_alphabetic())
+                && number.len() == 10
+                && number.chars().all(|c| c.is_ascii_digit())
+            {
+                Ok(compact)
+            } else {
+                Err(format!(
+                    "'{raw}' is not a receipt number (3 letters and 10 digits)"
+                ))
+            }
+        }
+        FormFieldKind::Phone => {
+            if (10..=15).contains(&digits.len()) {
+                Ok(digits)
+            } else {
+                Err(format!("'{raw}' is not a phone number"))
+            }
+        }
+        FormFieldKind::Email => match raw.split_once('@') {
+            Some((local, domain))
+                if !local.is_empty() && domain.contains('.') && !raw.contains(' ') =>
+            {
+                Ok(raw.to_string())
+            }
+            _ => Err(format!("'{raw}' is not an email address")),
+        },
+        FormFieldKind::Choice => field
+            .options
+            .iter()
+            .find(|option| option.eq_ignore_ascii_case(raw))
+            .cloned()
+            .ok_or_else(|| format!("'{raw}' must be one of: {}", field.options.join(", "))),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FilledField {
+    pub id: String,
+    pub label: String,
+    pub required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Data key the value was read from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<String>,
+    #[serde(skip_serializing)]
+    pdf_field: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FilledForm {
+    pub form_id: String,
+    pub title: String,
+    pub edition: String,
+    pub fields: Vec<FilledField>,
+    pub required_total: usize,
+    pub required_filled: usize,
+    /// Labels of required fields with no value.
+    pub missing: Vec<String>,
+    /// `label: problem` for values that failed validation.
+    pub invalid: Vec<String>,
+    pub complete: bool,
+}
+
+/// Fill `form` from flattened matter data.
+pub fn fill_form(form: &ImmigrationForm, data: &BTreeMap<String, String>) -> FilledForm {
+    let mut fields = Vec::with_capacity(form.fields.len());
+    let mut missing = Vec::new();
+    let mut invalid = Vec::new();
+    for field in &form.fields {
+        let found = field.sources.iter().find_map(|key| {
+            data.get(key)
+                .filter(|value| !value.trim().is_empty())
+                .map(|value| (key.clone(), value.clone()))
+        });
+        let (value, source, issue) = match found {
+            Some((key, raw)) => match normalize_field_value(field, &raw) {
+                Ok(value) => (Some(value), Some(key), None),
+                Err(problem) => {
+                    invalid.push(format!("{}: {problem}", field.label));
+                    (None, Some(key), Some(problem))
+                }
+            },
+            None => {
+                if field.required {
+                    missing.push(field.label.clone());
+                }
+                (None, None, field.required.then(|| "required".to_string()))
+            }
+        };
+        fields.push(FilledField {
+            id: field.id.clone(),
+            label: field.label.clone(),
+            required: field.required,
+            value,
+            source,
+            issue,
+            pdf_field: field.pdf_field.clone(),
+        });
+    }
+    let required_total = fields.iter().filter(|f| f.required).count();
+    let required_filled = fields
+        .iter()
+        .filter(|f| f.required && f.value.is_some())
+        .count();
+    FilledForm {
+        form_id: form.id.clone(),
+        title: form.title.clone(),
+        edition: form.edition.clone(),
+        complete: missing.is_empty() && invalid.is_empty(),
+        fields,
+        required_total,
+        required_filled,
+        missing,
+        invalid,
+    }
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// XFDF field data for the filled values, keyed by `pdf_field` when the
+/// form definition names one and by field id otherwise.
+pub fn render_xfdf(filled: &FilledForm) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<xfdf xmlns=\"http://ns.adobe.com/xfdf/\" xml:space=\"preserve\">\n  <fields>\n",
+    );
+    for field in &filled.fields {
+        let Some(value) = &field.value else {
+            continue;
+        };
+        out.push_str(&format!(
+            "    <field name=\"{}\"><value>{}</value></field>\n",
+            xml_escape(field.pdf_field.as_deref().unwrap_or(&field.id)),
+            xml_escape(value)
+        ));
+    }
+    out.push_str(&format!(
+        "  </fields>\n  <ids original=\"{}\" modified=\"{}\"/>\n</xfdf>\n",
+        xml_escape(&filled.form_id),
+        xml_escape(&filled.edition)
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CASE_DATA: &str = "\
+petition:
+  relationship: Spouse
+  marriage_date: 2021-06-19
+petitioner:
+  status: U.S. citizen
+  family_name: Rivera
+  given_name: Ana
+  date_of_birth: 1988-02-03
+  country_of_birth: United States
+  ssn: 123 45 6789
+beneficiary:
+  family_name: Okafor
+  given_name: Chidi
+  a_number: A-1234567
+  date_of_birth: 02/14/1987
+  country_of_birth: Nigeria
+";
+
+    #[test]
+    fn bundled_forms_parse_and_presets_exist() {
+        let forms = immigration_forms().expect("forms should parse");
+        for id in ["i-130", "i-485", "i-765", "n-400"] {
+            assert!(forms.iter().any(|form| form.id == id), "missing form {id}");
+        }
+        for form in forms {
+            for preset in &form.deadline_presets {
+                assert!(
+                    crate::legal::calendar::get_court_rule(preset)
+                        .expect("rules should parse")
+                        .is_some(),
+                    "form {} names unknown preset {preset}",
+                    form.id
+                );
+            }
+        }
+        assert_eq!(
+            get_immigration_form("I130")
+                .unwrap()
+                .map(|form| form.id.as_str()),
+            Some("i-130")
+        );
+    }
+
+    #[test]
+    fn fill_form_normalizes_values_and_reports_missing_fields() {
+        let mut data = parse_case_data(CASE_DATA).expect("case data");
+        data.insert(
+            "client.address".to_string(),
+            "12 Elm St, Springfield IL".to_string(),
+        );
+        data.insert("client.email".to_string(), "ana.rivera@example".to_string());
+        let form = get_immigration_form("i-130").unwrap().unwrap();
+        let filled = fill_form(form, &data);
+
+        let value = |id: &str| {
+            filled
+                .fields
+                .iter()
+                .find(|field| field.id == id)
+                .and_then(|field| field.value.clone())
+        };
+        assert_eq!(value("relationship").as_deref(), Some("spouse"));
+        assert_eq!(value("petitioner_ssn").as_deref(), Some("123-45-6789"));
+        assert_eq!(value("beneficiary_a_number").as_deref(), Some("A001234567"));
+        assert_eq!(
+            value("beneficiary_date_of_birth").as_deref(),
+            Some("02/14/1987")
+        );
+        assert_eq!(
+            value("petitioner_date_of_birth").as_deref(),
+            Some("02/03/1988")
+        );
+        assert_eq!(
+            value("petitioner_mailing_address").as_deref(),
+            Some("12 Elm St, Springfield IL")
+        );
+
+        assert!(!filled.complete);
+        assert_eq!(filled.missing, vec!["Part 4: Beneficiary physical address"]);
+        assert_eq!(filled.invalid.len(), 1);
+        assert!(filled.invalid[0].starts_with("Part 6: Petitioner email address"));
+        assert_eq!(filled.required_total, filled.required_filled + 1);
+
+        let xfdf = render_xfdf(&filled);
+        assert!(
+            xfdf.contains("<field name=\"beneficiary_family_name\"><value>Okafor</value></field>")
+        );
+        assert!(!xfdf.contains("beneficiary_address"));
+        assert!(!xfdf.contains("petitioner_email"));
+    }
+
+    #[test]
+    fn invalid_identifiers_are_rejected() {
+        let field = |kind| FormField {
+            id: "f".to_string(),
+            label: "F".to_string(),
+            sources: vec!["f".to_string()],
+            kind,
+            required: false,
+            options: Vec::new(),
+            pdf_field: None,
+        };
+        assert!(normalize_field_value(&field(FormFieldKind::ANumber), "A12345").is_err());
+        assert!(normalize_field_value(&field(FormFieldKind::Date), "June 2021").is_err());
+        assert_eq!(
+            normalize_field_value(&field(FormFieldKind::ReceiptNumber), "ioe-0912345678"),
+            Ok("IOE0912345678".to_string())
+        );
+        assert!(normalize_field_value(&field(FormFieldKind::ReceiptNumber), "IOE123").is_err());
+    }
+}
@@ -0,0 +1,405 @@
+//! IP docketing: statutory patent and trademark terms computed from
+//! application events.
+//!
+//! The rules live in the bundled `ip_rules.toml`, which the deadline engine
+//! loads alongside `court_rules.toml`, so each term is an ordinary
+//! [`CourtRule`] with a month offset. This module reads the IP-only fields
+//! of that file (right, trigger event, title, grace link) and turns an
+//! application's event dates into a docket plan for one office, optionally
+//! with the Paris Convention and PCT terms that follow any application.
+//! Saving a plan is the handler's job: it anchors each trigger event as a
+//! deadline and chains the computed terms to it, so correcting an event date
+//! recomputes every term through the existing cascade.
+
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::legal::calendar::{ComputationTrace, CourtRule, apply_rule_with_trace, get_court_rule};
+
+/// Rule jurisdiction for treaty terms that apply whatever the office.
+pub const TREATY_JURISDICTION: &str = "WIPO";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IpRight {
+    Patent,
+    Trademark,
+}
+
+impl IpRight {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Patent => "patent",
+            Self::Trademark => "trademark",
+        }
+    }
+
+    /// Parse `patent` / `trademark`, accepting `trade mark` and `tm`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "patent" | "patents" => Some(Self::Patent),
+            "trademark" | "trademarks" | "trade mark" | "trade_mark" | "tm" => {
+                Some(Self::Trademark)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Application event an IP term runs from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IpTrigger {
+    FilingDate,
+    PriorityDate,
+    GrantDate,
+    RegistrationDate,
+    OfficeActionDate,
+    AllowanceDate,
+}
+
+impl IpTrigger {
+    pub const ALL: [Self; 6] = [
+        Self::FilingDate,
+        Self::PriorityDate,
+        Self::GrantDate,
+        Self::RegistrationDate,
+        Self::OfficeActionDate,
+        Self::AllowanceDate,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::FilingDate => "filing_date",
+            Self::PriorityDate => "priority_date",
+            Self::GrantDate => "grant_date",
+            Self::RegistrationDate => "registration_date",
+            Self::OfficeActionDate => "office_action_date",
+            Self::AllowanceDate => "allowance_date",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::FilingDate => "filing date",
+            Self::PriorityDate => "priority date",
+            Self::GrantDate => "grant date",
+            Self::RegistrationDate => "registration date",
+            Self::OfficeActionDate => "office action date",
+            Self::AllowanceDate => "notice of allowance date",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpOffice {
+    pub code: &'static str,
+    pub name: &'static str,
+    /// Country and region codes that resolve to this office.
+    pub aliases: &'static [&'static str],
+}
+
+pub const IP_OFFICES: &[IpOffice] = &[
+    IpOffice {
+        code: "USPTO",
+        name: "United States Patent and Trademark Office",
+        aliases: &["US", "USA"],
+    },
+    IpOffice {
+        code: "EPO",
+        name: "European Patent Office",
+        aliases: &["EP"],
+    },
+    IpOffice {
+        code: "EUIPO",
+        name: "European Union Intellectual Property Office",
+        aliases: &["EU", "EM", "OHIM"],
+    },
+    IpOffice {
+        code: "UKIPO",
+        name: "UK Intellectual Property Office",
+        aliases: &["GB", "UK"],
+    },
+    IpOffice {
+        code: "CIPO",
+        name: "Canadian Intellectual Property Office",
+        aliases: &["CA", "CAN"],
+    },
+    IpOffice {
+        code: TREATY_JURISDICTION,
+        name: "Treaty terms (Paris Convention, PCT)",
+        aliases: &["WO", "PCT", "PARIS"],
+    },
+];
+
+/// Resolve an office code or country alias (`US`, `gb`, `EUIPO`).
+pub fn resolve_ip_office(value: &str) -> Option<&'static IpOffice> {
+    let wanted = value.trim();
+    IP_OFFICES.iter().find(|office| {
+        office.code.eq_ignore_ascii_case(wanted)
+            || office
+                .aliases
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(wanted))
+    })
+}
+
+/// IP fields of one `ip_rules.toml` entry; the term itself is the
+/// [`CourtRule`] with the same id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpRuleMeta {
+    pub id: String,
+    pub jurisdiction: String,
+    pub right: IpRight,
+    pub trigger: IpTrigger,
+    pub title: String,
+    /// Rule whose deadline this grace or extension window extends.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grace_for: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IpRulesConfig {
+    rules: Vec<IpRuleMeta>,
+}
+
+static IP_RULES: LazyLock<Result<Vec<IpRuleMeta>, String>> =
+    LazyLock::new(|| parse_ip_rules(include_str!("ip_rules.toml")));
+
+fn parse_ip_rules(raw: &str) -> Result<Vec<IpRuleMeta>, String> {
+    let parsed: IpRulesConfig =
+        toml::from_str(raw).map_err(|e| format!("invalid IP rules TOML: {e}"))?;
+    for rule in &parsed.rules {
+        if resolve_ip_office(&rule.jurisdiction).map(|office| office.code)
+            != Some(rule.jurisdiction.as_str())
+        {
+            return Err(format!(
+                "IP rule '{}' has unknown office '{}'",
+                rule.id, rule.jurisdiction
+            ));
+        }
+        if let Some(base) = rule.grace_for.as_deref()
+            && !parsed.rules.iter().any(|other| other.id == base)
+        {
+            return Err(format!(
+                "IP rule '{}' extends unknown rule '{base}'",
+                rule.id
+            ));
+        }
+    }
+    Ok(parsed.rules)
+}
+
+pub fn ip_rules() -> Result<&'static [IpRuleMeta], String> {
+    match &*IP_RULES {
+        Ok(rules) => Ok(rules.as_slice()),
+        Err(err) => Err(err.clone()),
+    }
+}
+
+/// IP metadata for a rule id, or `None` for ordinary court rules.
+pub fn ip_rule_meta(rule_id: &str) -> Result<Option<&'static IpRuleMeta>, String> {
+    Ok(ip_rules()?.iter().find(|rule| rule.id == rule_id))
+}
+
+/// Rules that apply to `right` at `office`, plus treaty terms when asked.
+pub fn ip_rules_for(
+    office: &IpOffice,
+    right: IpRight,
+    include_treaty: bool,
+) -> Result<Vec<&'static IpRuleMeta>, String> {
+    Ok(ip_rules()?
+        .iter()
+        .filter(|rule| rule.right == right)
+        .filter(|rule| {
+            rule.jurisdiction == office.code
+                || (include_treaty && rule.jurisdiction == TREATY_JURISDICTION)
+        })
+        .collect())
+}
+
+/// One computed term in a docket plan.
+#[derive(Debug, Clone)]
+pub struct IpDocketItem {
+    pub meta: &'static IpRuleMeta,
+    pub rule: CourtRule,
+    pub trigger_date: NaiveDate,
+    pub due_at: DateTime<Utc>,
+    pub trace: ComputationTrace,
+}
+
+/// Midnight UTC on `date`, the trigger instant used for IP terms.
+pub fn trigger_instant(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0)
+        .expect("midnight is a valid time")
+        .and_utc()
+}
+
+/// Compute every applicable term whose trigger event has a date, ordered
+/// by due date. Terms whose event is missing are left out.
+pub fn plan_ip_docket(
+    office: &IpOffice,
+    right: IpRight,
+    events: &BTreeMap<IpTrigger, NaiveDate>,
+    include_treaty: bool,
+) -> Result<Vec<IpDocketItem>, String> {
+    let mut items = Vec::new();
+    for meta in ip_rules_for(office, right, include_treaty)? {
+        let Some(trigger_date) = events.get(&meta.trigger).copied() else {
+            continue;
+        };
+        let rule = get_court_rule(&meta.id)?
+            .ok_or_else(|| format!("IP rule '{}' is missing from the rule table", meta.id))?;
+        let (due_at, trace) = apply_rule_with_trace(&rule, trigger_instant(trigger_date));
+        items.push(IpDocketItem {
+            meta,
+            rule,
+            trigger_date,
+            due_at,
+            trace,
+        });
+    }
+    items.sort_by(|a, b| {
+        a.due_at
+            .cmp(&b.due_at)
+            .then_with(|| a.meta.id.cmp(&b.meta.id))
+    });
+    Ok(items)
+}
+
+/// Label for an application in deadline titles, e.g. `USPTO patent 17/123,456`.
+pub fn application_label(
+    office: &IpOffice,
+    right: IpRight,
+    application_number: Option<&str>,
+) -> String {
+    match application_number
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        Some(number) => format!("{} {} {number}", office.code, right.as_str()),
+        None => format!("{} {}", office.code, right.as_str()),
+    }
+}
+
+/// `rule_ref` stored on the anchor deadline for a trigger event, so a later
+/// docket run for the same application reuses it.
+pub fn anchor_rule_ref(
+    office: &IpOffice,
+    right: IpRight,
+    trigger: IpTrigger,
+    application_number: Option<&str>,
+) -> String {
+    let mut out = format!(
+        "ip-anchor:{}:{}:{}",
+        office.code,
+        right.as_str(),
+        trigger.as_str()
+    );
+    if let Some(number) = application_number
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        out.push(':');
+        out.push_str(number);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").expect("valid date")
+    }
+
+    #[test]
+    fn bundled_ip_rules_parse_and_exist_in_rule_table() {
+        let rules = ip_rules().expect("IP rules should parse");
+        assert!(rules.len() >= 20);
+        for meta in rules {
+            let rule = get_court_rule(&meta.id)
+                .expect("court rules should parse")
+                .unwrap_or_else(|| panic!("missing court rule {}", meta.id));
+            assert!(rule.offset_months > 0, "{} should be a month term", meta.id);
+            assert!(!rule.court_days, "{} should count calendar time", meta.id);
+        }
+        assert_eq!(resolve_ip_office("gb").map(|o| o.code), Some("UKIPO"));
+        assert_eq!(resolve_ip_office("US").map(|o| o.code), Some("USPTO"));
+        assert!(resolve_ip_office("XX").is_none());
+    }
+
+    #[test]
+    fn uspto_patent_plan_clamps_month_ends_and_rolls_weekends() {
+        let office = resolve_ip_office("USPTO").expect("office");
+        let mut events = BTreeMap::new();
+        // Office action mailed Friday 2025-11-28: three months lands on
+        // Saturday 2026-02-28 and rolls to Monday 2026-03-02.
+        events.insert(IpTrigger::OfficeActionDate, date("2025-11-28"));
+        // Granted 2024-08-31: 3.5 years is 2028-02-29 (leap-year month end).
+        events.insert(IpTrigger::GrantDate, date("2024-08-31"));
+
+        let plan = plan_ip_docket(office, IpRight::Patent, &events, false).expect("plan");
+        let due = |id: &str| {
+            plan.iter()
+                .find(|item| item.meta.id == id)
+                .map(|item| item.due_at.date_naive().to_string())
+                .unwrap_or_else(|| panic!("missing {id}"))
+        };
+        assert_eq!(due("uspto_patent_office_action"), "2026-03-02");
+        assert_eq!(due("uspto_patent_office_action_max"), "2026-05-28");
+        assert_eq!(due("uspto_maintenance_fee_3_5"), "2028-02-29");
+        assert_eq!(due("uspto_maintenance_fee_3_5_surcharge"), "2028-08-31");
+        assert!(plan.iter().all(|item| item.meta.right == IpRight::Patent));
+        assert!(!plan.iter().any(|item| item.meta.id == "pct_national_phase"));
+        assert!(
+            plan.windows(2).all(|pair| pair[0].due_at <= pair[1].due_at),
+            "plan should be ordered by due date"
+        );
+        let trace = &plan[0].trace;
+        assert_eq!(trace.offset_months, 3);
+        assert!(
+            trace
+                .steps
+                .iter()
+                .any(|step| step.description.contains("calendar months"))
+        );
+    }
+
+    #[test]
+    fn foreign_trademark_variants_and_treaty_terms() {
+        let euipo = resolve_ip_office("EU").expect("office");
+        let ukipo = resolve_ip_office("UK").expect("office");
+        let mut events = BTreeMap::new();
+        events.insert(IpTrigger::FilingDate, date("2016-03-15"));
+        events.insert(IpTrigger::PriorityDate, date("2016-03-15"));
+
+        let eu = plan_ip_docket(euipo, IpRight::Trademark, &events, true).expect("plan");
+        let ids = eu
+            .iter()
+            .map(|item| item.meta.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                "paris_priority_trademark",
+                "euipo_tm_renewal",
+                "euipo_tm_renewal_grace"
+            ]
+        );
+        assert_eq!(eu[1].due_at.date_naive().to_string(), "2026-03-16");
+
+        let uk = plan_ip_docket(ukipo, IpRight::Trademark, &events, false).expect("plan");
+        assert!(uk.iter().all(|item| item.meta.jurisdiction == "UKIPO"));
+        assert_eq!(
+            ip_rule_meta("ukipo_tm_renewal_grace")
+                .expect("rules")
+                .and_then(|meta| meta.grace_for.as_deref()),
+            Some("ukipo_tm_renewal")
+        );
+    }
+}
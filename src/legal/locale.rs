@@ -0,0 +1,438 @@
+//! Locales for client-facing documents and correspondence.
+//!
+//! A client's communication preferences name the language they read (see
+//! [`crate::legal::client_comms`]). This module turns that into a
+//! [`Locale`], which picks the per-language template variant
+//! (`engagement_letter.md` -> `engagement_letter.es.md`), formats dates and
+//! numbers in the docgen filters, and tells a drafting model which language
+//! and conventions to use. Languages without a locale here still get a
+//! drafting instruction; only formatting falls back to US English.
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Locales with template variants and date/number formatting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    #[serde(rename = "en")]
+    EnUs,
+    #[serde(rename = "en-GB")]
+    EnGb,
+    #[serde(rename = "es")]
+    Es,
+    #[serde(rename = "fr")]
+    Fr,
+    #[serde(rename = "de")]
+    De,
+    #[serde(rename = "pt")]
+    Pt,
+    #[serde(rename = "it")]
+    It,
+}
+
+/// Date styles shared with the `legal_date` docgen filter.
+pub const DATE_STYLES: [&str; 4] = ["long", "ordinal", "short", "iso"];
+
+impl Locale {
+    pub const ALL: [Locale; 7] = [
+        Locale::EnUs,
+        Locale::EnGb,
+        Locale::Es,
+        Locale::Fr,
+        Locale::De,
+        Locale::Pt,
+        Locale::It,
+    ];
+
+    /// Tag used in template variant names and the docgen `locale` context.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::EnUs => "en",
+            Self::EnGb => "en-GB",
+            Self::Es => "es",
+            Self::Fr => "fr",
+            Self::De => "de",
+            Self::Pt => "pt",
+            Self::It => "it",
+        }
+    }
+
+    /// Parse a language tag (`es`, `es-MX`, `en_GB`) or an English or
+    /// native language name (`Spanish`, `español`).
+    pub fn parse(raw: &str) -> Option<Self> {
+        let lowered = raw.trim().to_lowercase();
+        let by_name = match lowered.as_str() {
+            "english" => Some(Self::EnUs),
+            "british english" => Some(Self::EnGb),
+            "spanish" | "español" | "espanol" => Some(Self::Es),
+            "french" | "français" | "francais" => Some(Self::Fr),
+            "german" | "deutsch" => Some(Self::De),
+            "portuguese" | "português" | "portugues" => Some(Self::Pt),
+            "italian" | "italiano" => Some(Self::It),
+            _ => None,
+        };
+        if by_name.is_some() {
+            return by_name;
+        }
+        let tag = crate::legal::translation::normalize_language_tag(raw)?;
+        let mut parts = tag.split('-');
+        match parts.next()? {
+            "en" => Some(
+                if parts.any(|region| matches!(region, "GB" | "IE" | "AU" | "NZ")) {
+                    Self::EnGb
+                } else {
+                    Self::EnUs
+                },
+            ),
+            "es" => Some(Self::Es),
+            "fr" => Some(Self::Fr),
+            "de" => Some(Self::De),
+            "pt" => Some(Self::Pt),
+            "it" => Some(Self::It),
+            _ => None,
+        }
+    }
+
+    /// English name, with the native name for other languages.
+    pub fn language_name(self) -> &'static str {
+        match self {
+            Self::EnUs => "English",
+            Self::EnGb => "British English",
+            Self::Es => "Spanish (español)",
+            Self::Fr => "French (français)",
+            Self::De => "German (Deutsch)",
+            Self::Pt => "Portuguese (português)",
+            Self::It => "Italian (italiano)",
+        }
+    }
+
+    fn month_name(self, month: u32) -> &'static str {
+        const EN: [&str; 12] = [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ];
+        const ES: [&str; 12] = [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ];
+        const FR: [&str; 12] = [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ];
+        const DE: [&str; 12] = [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ];
+        const PT: [&str; 12] = [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ];
+        const IT: [&str; 12] = [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ];
+        let names = match self {
+            Self::EnUs | Self::EnGb => &EN,
+            Self::Es => &ES,
+            Self::Fr => &FR,
+            Self::De => &DE,
+            Self::Pt => &PT,
+            Self::It => &IT,
+        };
+        names[(month.clamp(1, 12) - 1) as usize]
+    }
+
+    /// Format `date` in one of [`DATE_STYLES`]: `long` (`March 2, 2026`,
+    /// `2 de marzo de 2026`), `ordinal` (`2nd day of March, 2026`), `short`
+    /// (`03/02/2026`, `02.03.2026`), or `iso`.
+    pub fn format_date(self, date: NaiveDate, style: &str) -> Result<String, String> {
+        let (day, month, year) = (date.day(), self.month_name(date.month()), date.year());
+        let formatted = match (style, self) {
+            ("long", Self::EnUs) => format!("{month} {day}, {year}"),
+            ("long", Self::EnGb | Self::Fr | Self::It) => format!("{day} {month} {year}"),
+            ("long", Self::Es | Self::Pt) => format!("{day} de {month} de {year}"),
+            ("long", Self::De) => format!("{day}. {month} {year}"),
+            ("ordinal", Self::EnUs) => {
+                format!("{day}{} day of {month}, {year}", ordinal_suffix(day))
+            }
+            ("ordinal", Self::EnGb) => {
+                format!("{day}{} day of {month} {year}", ordinal_suffix(day))
+            }
+            ("ordinal", Self::Es) => format!("a los {day} días del mes de {month} de {year}"),
+            ("ordinal", Self::Fr) => {
+                let day = if day == 1 {
+                    "1er".to_string()
+                } else {
+                    day.to_string()
+                };
+                format!("le {day} {month} {year}")
+            }
+            ("ordinal", Self::De) => format!("am {day}. {month} {year}"),
+            ("ordinal", Self::Pt) => format!("aos {day} dias do mês de {month} de {year}"),
+            ("ordinal", Self::It) => format!("il giorno {day} {month} {year}"),
+            ("short", Self::EnUs) => date.format("%m/%d/%Y").to_string(),
+            ("short", Self::De) => date.format("%d.%m.%Y").to_string(),
+            ("short", _) => date.format("%d/%m/%Y").to_string(),
+            ("iso", _) => date.to_string(),
+            (other, _) => {
+                return Err(format!(
+                    "date style must be {}, got '{other}'",
+                    DATE_STYLES.join(", ")
+                ));
+            }
+        };
+        Ok(formatted)
+    }
+
+    /// Thousands and decimal separators.
+    fn separators(self) -> (&'static str, char) {
+        match self {
+            Self::EnUs | Self::EnGb => (",", '.'),
+            // CLDR groups French digits with a narrow no-break space.
+            Self::Fr => ("\u{202f}", ','),
+            Self::Es | Self::De | Self::Pt | Self::It => (".", ','),
+        }
+    }
+
+    /// `1234567.5` -> `1,234,567.50` (en) or `1.234.567,50` (es).
+    pub fn format_number(self, value: f64, decimals: usize) -> String {
+        let formatted = format!("{:.*}", decimals, value.abs());
+        let (whole, fraction) = match formatted.split_once('.') {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (formatted.as_str(), None),
+        };
+        let (group, decimal) = self.separators();
+        let mut out = String::new();
+        if value < 0.0 && formatted.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            out.push('-');
+        }
+        for (i, digit) in whole.chars().enumerate() {
+            if i > 0 && (whole.len() - i) % 3 == 0 {
+                out.push_str(group);
+            }
+            out.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            out.push(decimal);
+            out.push_str(fraction);
+        }
+        out
+    }
+
+    /// Amount with `symbol` placed the way the locale writes money:
+    /// `$1,234.50` in English, `1.234,50 €` in Spanish.
+    pub fn format_currency(self, value: f64, symbol: &str, decimals: usize) -> String {
+        let number = self.format_number(value, decimals);
+        if symbol.is_empty() {
+            return number;
+        }
+        match self {
+            Self::EnUs | Self::EnGb => match number.strip_prefix('-') {
+                Some(unsigned) => format!("-{symbol}{unsigned}"),
+                None => format!("{symbol}{number}"),
+            },
+            Self::Es | Self::Fr | Self::De | Self::Pt | Self::It => {
+                format!("{number}\u{a0}{symbol}")
+            }
+        }
+    }
+}
+
+fn ordinal_suffix(day: u32) -> &'static str {
+    match (day % 10, day % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+/// Locale for a client's preferred `language`; US English when unset or
+/// not one of [`Locale::ALL`].
+pub fn client_locale(language: Option<&str>) -> Locale {
+    language.and_then(Locale::parse).unwrap_or_default()
+}
+
+/// Name of the `locale` variant of template `name`: `letter.md` ->
+/// `letter.es.md`. US English is the base template itself.
+pub fn template_variant_name(name: &str, locale: Locale) -> String {
+    if locale == Locale::EnUs {
+        return name.to_string();
+    }
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => format!("{stem}.{}.{ext}", locale.code()),
+        _ => format!("{name}.{}", locale.code()),
+    }
+}
+
+/// Instruction for a model writing to a client who reads `language`, or
+/// `None` for US English. Known locales also get their date and number
+/// conventions spelled out.
+pub fn drafting_instruction(language: &str, today: NaiveDate) -> Option<String> {
+    let language = language.trim();
+    if language.is_empty() {
+        return None;
+    }
+    let Some(locale) = Locale::parse(language) else {
+        return Some(format!("Write the message in {language}."));
+    };
+    if locale == Locale::EnUs {
+        return None;
+    }
+    let date = locale.format_date(today, "long").unwrap_or_default();
+    Some(format!(
+        "Write the message in {}. Write dates like \"{date}\" and amounts like \"{}\".",
+        locale.language_name(),
+        locale.format_number(1234.5, 2),
+    ))
+}
+
+/// System prompt section for a conversation with a client who reads
+/// `language`, or `None` when no instruction is needed.
+pub fn client_reply_addendum(language: &str, today: NaiveDate) -> Option<String> {
+    drafting_instruction(language, today).map(|instruction| {
+        format!(
+            "## Client Language\n\nReplies in this conversation go to a client. {instruction} \
+             Keep matter names, case captions, and quoted documents as written."
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{Locale, client_locale, drafting_instruction, template_variant_name};
+
+    #[test]
+    fn tags_and_names_parse_to_locales() {
+        assert_eq!(Locale::parse("es-MX"), Some(Locale::Es));
+        assert_eq!(Locale::parse("Español"), Some(Locale::Es));
+        assert_eq!(Locale::parse("en_gb"), Some(Locale::EnGb));
+        assert_eq!(Locale::parse("en-US"), Some(Locale::EnUs));
+        assert_eq!(Locale::parse("ja"), None);
+        assert_eq!(client_locale(Some("ja")), Locale::EnUs);
+        assert_eq!(client_locale(Some("pt-BR")), Locale::Pt);
+        for locale in Locale::ALL {
+            assert_eq!(Locale::parse(locale.code()), Some(locale));
+        }
+    }
+
+    #[test]
+    fn dates_and_numbers_follow_the_locale() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let long = |locale: Locale| locale.format_date(date, "long").unwrap();
+        assert_eq!(long(Locale::EnUs), "March 2, 2026");
+        assert_eq!(long(Locale::EnGb), "2 March 2026");
+        assert_eq!(long(Locale::Es), "2 de marzo de 2026");
+        assert_eq!(long(Locale::De), "2. März 2026");
+        assert_eq!(
+            Locale::Es.format_date(date, "ordinal").unwrap(),
+            "a los 2 días del mes de marzo de 2026"
+        );
+        assert_eq!(Locale::De.format_date(date, "short").unwrap(), "02.03.2026");
+        assert_eq!(Locale::Fr.format_date(date, "short").unwrap(), "02/03/2026");
+        assert!(Locale::Fr.format_date(date, "full").is_err());
+
+        assert_eq!(Locale::EnUs.format_number(1234567.5, 2), "1,234,567.50");
+        assert_eq!(Locale::Es.format_number(1234567.5, 2), "1.234.567,50");
+        assert_eq!(Locale::Fr.format_number(-1234.0, 0), "-1\u{202f}234");
+        assert_eq!(Locale::EnUs.format_currency(-12.5, "$", 2), "-$12.50");
+        assert_eq!(
+            Locale::De.format_currency(1234.5, "€", 2),
+            "1.234,50\u{a0}€"
+        );
+    }
+
+    #[test]
+    fn variants_and_drafting_instructions() {
+        assert_eq!(
+            template_variant_name("engagement_letter.md", Locale::Es),
+            "engagement_letter.es.md"
+        );
+        assert_eq!(
+            template_variant_name("engagement", Locale::EnGb),
+            "engagement.en-GB"
+        );
+        assert_eq!(
+            template_variant_name("engagement.md", Locale::EnUs),
+            "engagement.md"
+        );
+
+        let today = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        assert_eq!(
+            drafting_instruction("es", today).as_deref(),
+            Some(
+                "Write the message in Spanish (español). Write dates like \
+                 \"15 de octubre de 2026\" and amounts like \"1.234,50\"."
+            )
+        );
+        assert_eq!(
+            drafting_instruction("ja", today).as_deref(),
+            Some("Write the message in ja.")
+        );
+        assert_eq!(drafting_instruction("en-US", today), None);
+    }
+}
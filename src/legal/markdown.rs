@@ -0,0 +1,26 @@
+//! Markdown helpers shared by the legal document renderers.
+
+/// Escape text for one markdown table cell: whitespace runs, line breaks
+/// included, collapse to a single space and pipes are escaped.
+pub fn table_cell(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_cell_stays_on_one_row() {
+        assert_eq!(table_cell("a | b"), "a \\| b");
+        assert_eq!(
+            table_cell("  line one\r\nline\ttwo \n"),
+            "line one line two"
+        );
+        assert_eq!(table_cell(""), "");
+    }
+}
@@ -586,7 +586,7 @@ pub async fn seed_legal_workspace(
         ),
         (
             format!("{prefix}/communications/contact_log.md"),
-            "# Communications Log\n\n| Date | With | Channel | Summary | Follow-up |\n|---|---|---|---|---|\n".to_string(),
+            crate::legal::correspondence::CONTACT_LOG_TEMPLATE.to_string(),
         ),
         (
             format!("{prefix}/templates/research_memo.md"),
@@ -611,6 +611,12 @@ pub async fn seed_legal_workspace(
         (
             format!("{prefix}/templates/legal_memo.md"),
             "# Legal Memo\n\n## Issue\n\n## Brief Answer\n\n## Facts (Cited)\n\n## Analysis\n\n## Conclusion\n\n## Uncertainty/Risk\n".to_string(),
+        ),        (
+            format!(
+                "{prefix}/templates/{}",
+                crate::legal::status_report::TEMPLATE_NAME
+            ),
+            crate::legal::status_report::TEMPLATE_BODY.to_string(),
         ),
     ];
 
@@ -0,0 +1,681 @@
+//! Medical records review: visit extraction and the treating-provider
+//! chronology for personal injury matters.
+//!
+//! Records are read from the matter's `medical/records/` folder. The
+//! workspace stores text, so scanned PDFs must be OCR'd or exported to text
+//! before upload; page breaks are taken from form feeds or `Page N` header
+//! or footer lines so every visit cites the pages it was read from. Visits
+//! start at a date-of-service label (`DOS:`, `Date of Service:`,
+//! `Admission Date:`, ...) and collect the provider, facility, visit type,
+//! and diagnoses (with ICD-10 codes) labelled after it. Labels before the
+//! first visit, such as a facility letterhead, apply to every visit in the
+//! file that does not name its own.
+
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::legal::markdown::table_cell;
+
+/// Folder under the matter that holds the records to review.
+pub const MEDICAL_RECORDS_FOLDER: &str = "medical/records";
+
+const MAX_FIELD_CHARS: usize = 160;
+
+/// `Page 3`, `Page 3 of 12`, `- Page 3 -`, `## Page 3`.
+static PAGE_MARKER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^[\s#*=\-]*page\s+(\d{1,4})(?:\s*(?:of|/)\s*\d{1,4})?[\s*=\-]*$")
+        .expect("valid page marker regex")
+});
+
+/// `Label: value`, tolerating list bullets and markdown bold around the
+/// label.
+static LABEL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*(?:[-*•]\s+)?(?:\*\*)?([A-Za-z][A-Za-z /&()\-]{0,40}?)(?:\*\*)?\s*:(?:\*\*)?\s*(.*)$",
+    )
+    .expect("valid label regex")
+});
+
+/// Column gaps separating several labels on one line.
+static SEGMENT_SPLIT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\s{3,}|\t+|\s\|\s").expect("valid segment regex"));
+
+static LIST_ITEM_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(?:[-*•]|\d{1,2}[.)])\s+(.+)$").expect("valid list regex"));
+
+static ICD10_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b([A-TV-Z][0-9][0-9A-Z](?:\.[0-9A-Z]{1,4})?)\b").expect("valid ICD-10 regex")
+});
+
+/// `03/14/2024`, `2024/03/14`, `14.03.2024`.
+static NUMERIC_DATE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(\d{1,4})[/.\-](\d{1,2})[/.\-](\d{2,4})\b").expect("valid numeric date regex")
+});
+
+pub fn medical_records_prefix(matter_prefix: &str) -> String {
+    format!("{matter_prefix}/{MEDICAL_RECORDS_FOLDER}")
+}
+
+pub fn treatment_chronology_path(matter_prefix: &str) -> String {
+    format!("{matter_prefix}/medical/treatment_chronology.md")
+}
+
+/// Structured visits behind the chronology, for reuse by other tools.
+pub fn medical_visits_path(matter_prefix: &str) -> String {
+    format!("{matter_prefix}/medical/medical_visits.json")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnosis {
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icd10: Option<String>,
+}
+
+/// One encounter found in a record, with the pages it was read from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MedicalVisit {
+    pub date: NaiveDate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub facility: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visit_type: Option<String>,
+    #[serde(default)]
+    pub diagnoses: Vec<Diagnosis>,
+    /// Workspace path of the record.
+    pub source: String,
+    pub page_start: u32,
+    pub page_end: u32,
+}
+
+impl MedicalVisit {
+    /// `er_records.md p. 3` or `er_records.md pp. 3-4`.
+    pub fn citation(&self) -> String {
+        let name = self.source.rsplit('/').next().unwrap_or(&self.source);
+        if self.page_start == self.page_end {
+            format!("{name} p. {}", self.page_start)
+        } else {
+            format!("{name} pp. {}-{}", self.page_start, self.page_end)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    VisitDate,
+    Provider,
+    Facility,
+    VisitType,
+    Diagnosis,
+}
+
+fn field_for_label(label: &str) -> Option<Field> {
+    let label = label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_ascii_lowercase();
+    let field = match label.as_str() {
+        "date of service" | "dos" | "service date" | "visit date" | "date of visit"
+        | "encounter date" | "date of encounter" | "admission date" | "admit date"
+        | "date of admission" | "exam date" | "date of exam" | "appointment date" | "date" => {
+            Field::VisitDate
+        }
+        "provider"
+        | "treating provider"
+        | "rendering provider"
+        | "attending"
+        | "attending physician"
+        | "physician"
+        | "treating physician"
+        | "examining physician"
+        | "seen by"
+        | "clinician"
+        | "practitioner"
+        | "therapist"
+        | "doctor"
+        | "surgeon" => Field::Provider,
+        "facility" | "facility name" | "hospital" | "clinic" | "location" | "practice" => {
+            Field::Facility
+        }
+        "visit type" | "encounter type" | "type of visit" | "department" => Field::VisitType,
+        "diagnosis"
+        | "diagnoses"
+        | "dx"
+        | "assessment"
+        | "impression"
+        | "impressions"
+        | "clinical impression"
+        | "assessment and plan"
+        | "assessment/plan"
+        | "final diagnosis"
+        | "discharge diagnosis"
+        | "discharge diagnoses"
+        | "primary diagnosis"
+        | "secondary diagnosis"
+        | "working diagnosis" => Field::Diagnosis,
+        _ => return None,
+    };
+    Some(field)
+}
+
+/// Split a record into numbered pages. Form feeds win; otherwise `Page N`
+/// lines are headers when the first one comes before any text and footers
+/// when it follows text. Without either, the record is one page.
+fn split_pages(content: &str) -> Vec<(u32, Vec<&str>)> {
+    if content.contains('\x0c') {
+        return content
+            .split('\x0c')
+            .enumerate()
+            .map(|(index, page)| (index as u32 + 1, page.lines().collect()))
+            .collect();
+    }
+    let lines: Vec<&str> = content.lines().collect();
+    let markers: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            PAGE_MARKER_RE
+                .captures(line)
+                .and_then(|caps| caps[1].parse().ok())
+                .map(|page| (index, page))
+        })
+        .collect();
+    let Some(&(first_marker, _)) = markers.first() else {
+        return vec![(1, lines)];
+    };
+
+    let mut pages = Vec::new();
+    if lines[..first_marker]
+        .iter()
+        .all(|line| line.trim().is_empty())
+    {
+        for (position, &(index, page)) in markers.iter().enumerate() {
+            let end = markers
+                .get(position + 1)
+                .map_or(lines.len(), |&(next, _)| next);
+            pages.push((page, lines[index + 1..end].to_vec()));
+        }
+    } else {
+        let mut start = 0;
+        for &(index, page) in &markers {
+            pages.push((page, lines[start..index].to_vec()));
+            start = index + 1;
+        }
+        if lines[start..].iter().any(|line| !line.trim().is_empty()) {
+            let next = markers.last().map_or(1, |&(_, page)| page + 1);
+            pages.push((next, lines[start..].to_vec()));
+        }
+    }
+    pages
+}
+
+/// Date written in a record field: month names, ISO, or numeric dates
+/// (`MM/DD/YYYY` unless the first number cannot be a month).
+pub fn parse_record_date(value: &str) -> Option<NaiveDate> {
+    if let Some(mention) = crate::legal::chronology::find_dates(value).first() {
+        return Some(mention.date);
+    }
+    let caps = NUMERIC_DATE_RE.captures(value)?;
+    let (a, b, c) = (&caps[1], &caps[2], &caps[3]);
+    let (a_num, b_num, c_num): (u32, u32, i32) =
+        (a.parse().ok()?, b.parse().ok()?, c.parse().ok()?);
+    if a.len() == 4 {
+        return NaiveDate::from_ymd_opt(a_num as i32, b_num, c_num as u32);
+    }
+    let year = match c.len() {
+        4 => c_num,
+        2 => {
+            // Two-digit years are this century unless that is in the future.
+            let current = Utc::now().year();
+            let candidate = current - current % 100 + c_num;
+            if candidate > current {
+                candidate - 100
+            } else {
+                candidate
+            }
+        }
+        _ => return None,
+    };
+    if a_num <= 12 {
+        NaiveDate::from_ymd_opt(year, a_num, b_num)
+    } else {
+        NaiveDate::from_ymd_opt(year, b_num, a_num)
+    }
+}
+
+fn clean_value(value: &str) -> Option<String> {
+    let value = value
+        .trim()
+        .trim_matches(|c: char| c == '*' || c == '_')
+        .trim()
+        .trim_end_matches([',', ';'])
+        .trim();
+    if value.is_empty() {
+        return None;
+    }
+    Some(value.chars().take(MAX_FIELD_CHARS).collect())
+}
+
+/// Diagnosis text with any ICD-10 code pulled out, e.g.
+/// `Cervical strain (S13.4XXA)`.
+fn parse_diagnosis(text: &str) -> Option<Diagnosis> {
+    let icd10 = ICD10_RE.captures(text).map(|caps| caps[1].to_string());
+    let mut description = match &icd10 {
+        Some(code) => text.replacen(code.as_str(), "", 1),
+        None => text.to_string(),
+    };
+    description = description
+        .replace("ICD-10", "")
+        .replace("()", "")
+        .replace("[]", "");
+    let description = description
+        .trim()
+        .trim_matches(|c: char| c == '-' || c == '–' || c == ':' || c == ',' || c.is_whitespace())
+        .to_string();
+    match (clean_value(&description), icd10) {
+        (Some(description), icd10) => Some(Diagnosis { description, icd10 }),
+        (None, Some(code)) => Some(Diagnosis {
+            description: code.clone(),
+            icd10: Some(code),
+        }),
+        (None, None) => None,
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct VisitFields {
+    provider: Option<String>,
+    facility: Option<String>,
+    visit_type: Option<String>,
+    diagnoses: Vec<Diagnosis>,
+}
+
+impl VisitFields {
+    fn set(&mut self, field: Field, value: &str) {
+        let slot = match field {
+            Field::Provider => &mut self.provider,
+            Field::Facility => &mut self.facility,
+            Field::VisitType => &mut self.visit_type,
+            Field::Diagnosis => {
+                for part in value.split(';') {
+                    self.add_diagnosis(part);
+                }
+                return;
+            }
+            Field::VisitDate => return,
+        };
+        if slot.is_none() {
+            *slot = clean_value(value);
+        }
+    }
+
+    fn add_diagnosis(&mut self, text: &str) {
+        if let Some(diagnosis) = parse_diagnosis(text)
+            && !self
+                .diagnoses
+                .iter()
+                .any(|d| d.description.eq_ignore_ascii_case(&diagnosis.description))
+        {
+            self.diagnoses.push(diagnosis);
+        }
+    }
+}
+
+struct OpenVisit {
+    date: NaiveDate,
+    page_start: u32,
+    page_end: u32,
+    fields: VisitFields,
+}
+
+impl OpenVisit {
+    fn finish(self, defaults: &VisitFields, source: &str) -> MedicalVisit {
+        MedicalVisit {
+            date: self.date,
+            provider: self.fields.provider.or_else(|| defaults.provider.clone()),
+            facility: self.fields.facility.or_else(|| defaults.facility.clone()),
+            visit_type: self
+                .fields
+                .visit_type
+                .or_else(|| defaults.visit_type.clone()),
+            diagnoses: self.fields.diagnoses,
+            source: source.to_string(),
+            page_start: self.page_start,
+            page_end: self.page_end,
+        }
+    }
+}
+
+/// Visits found in one record, in document order.
+pub fn extract_visits(source: &str, content: &str) -> Vec<MedicalVisit> {
+    let mut visits = Vec::new();
+    let mut defaults = VisitFields::default();
+    let mut open: Option<OpenVisit> = None;
+    // Set while reading list items under an empty `Diagnoses:` label.
+    let mut in_diagnosis_list = false;
+
+    for (page, lines) in split_pages(content) {
+        for line in lines {
+            if line.trim().is_empty() {
+                in_diagnosis_list = false;
+                continue;
+            }
+            let labelled: Vec<(Field, String)> = SEGMENT_SPLIT_RE
+                .split(line.trim())
+                .filter_map(|segment| {
+                    let caps = LABEL_RE.captures(segment)?;
+                    Some((field_for_label(&caps[1])?, caps[2].to_string()))
+                })
+                .collect();
+            if labelled.is_empty() {
+                if in_diagnosis_list {
+                    let item = LIST_ITEM_RE.captures(line).map(|caps| caps[1].to_string());
+                    match item {
+                        Some(item) => {
+                            let fields = match open.as_mut() {
+                                Some(visit) => {
+                                    visit.page_end = page;
+                                    &mut visit.fields
+                                }
+                                None => &mut defaults,
+                            };
+                            fields.add_diagnosis(&item);
+                        }
+                        None => in_diagnosis_list = false,
+                    }
+                }
+                continue;
+            }
+
+            in_diagnosis_list = false;
+            for (field, value) in labelled {
+                if field == Field::VisitDate {
+                    let Some(date) = parse_record_date(&value) else {
+                        continue;
+                    };
+                    // Page headers often repeat the date of service.
+                    if let Some(visit) = open.as_mut()
+                        && visit.date == date
+                    {
+                        visit.page_end = page;
+                        continue;
+                    }
+                    if let Some(done) = open.take() {
+                        visits.push(done.finish(&defaults, source));
+                    }
+                    open = Some(OpenVisit {
+                        date,
+                        page_start: page,
+                        page_end: page,
+                        fields: VisitFields::default(),
+                    });
+                    continue;
+                }
+                let fields = match open.as_mut() {
+                    Some(visit) => {
+                        visit.page_end = page;
+                        &mut visit.fields
+                    }
+                    None => &mut defaults,
+                };
+                if field == Field::Diagnosis && value.trim().is_empty() {
+                    in_diagnosis_list = true;
+                }
+                fields.set(field, &value);
+            }
+        }
+    }
+    if let Some(done) = open.take() {
+        visits.push(done.finish(&defaults, source));
+    }
+    visits
+}
+
+/// Order visits by date, then provider, then source and page.
+pub fn sort_visits(visits: &mut [MedicalVisit]) {
+    visits.sort_by(|a, b| {
+        a.date
+            .cmp(&b.date)
+            .then_with(|| a.provider.cmp(&b.provider))
+            .then_with(|| a.source.cmp(&b.source))
+            .then_with(|| a.page_start.cmp(&b.page_start))
+    });
+}
+
+/// One treating provider across all records.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProviderSummary {
+    pub provider: String,
+    pub facilities: Vec<String>,
+    pub first_visit: NaiveDate,
+    pub last_visit: NaiveDate,
+    pub visit_count: usize,
+    pub diagnoses: Vec<Diagnosis>,
+}
+
+fn provider_key(visit: &MedicalVisit) -> String {
+    match (&visit.provider, &visit.facility) {
+        (Some(provider), _) => provider.clone(),
+        (None, Some(facility)) => format!("Unnamed provider ({facility})"),
+        (None, None) => "Unnamed provider".to_string(),
+    }
+}
+
+/// Treating providers in order of first visit, from sorted visits.
+pub fn provider_summaries(visits: &[MedicalVisit]) -> Vec<ProviderSummary> {
+    let mut summaries: Vec<ProviderSummary> = Vec::new();
+    for visit in visits {
+        let provider = provider_key(visit);
+        let index = match summaries
+            .iter()
+            .position(|s| s.provider.eq_ignore_ascii_case(&provider))
+        {
+            Some(index) => index,
+            None => {
+                summaries.push(ProviderSummary {
+                    provider,
+                    facilities: Vec::new(),
+                    first_visit: visit.date,
+                    last_visit: visit.date,
+                    visit_count: 0,
+                    diagnoses: Vec::new(),
+                });
+                summaries.len() - 1
+            }
+        };
+        let summary = &mut summaries[index];
+        summary.first_visit = summary.first_visit.min(visit.date);
+        summary.last_visit = summary.last_visit.max(visit.date);
+        summary.visit_count += 1;
+        if let Some(facility) = &visit.facility
+            && !summary.facilities.contains(facility)
+        {
+            summary.facilities.push(facility.clone());
+        }
+        for diagnosis in &visit.diagnoses {
+            if !summary.diagnoses.contains(diagnosis) {
+                summary.diagnoses.push(diagnosis.clone());
+            }
+        }
+    }
+    summaries.sort_by_key(|a| a.first_visit);
+    summaries
+}
+
+fn diagnoses_cell(diagnoses: &[Diagnosis]) -> String {
+    diagnoses
+        .iter()
+        .map(|d| match &d.icd10 {
+            Some(code) if *code != d.description => format!("{} ({code})", d.description),
+            _ => d.description.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Markdown treating-provider chronology with page citations.
+pub fn render_treatment_chronology(
+    matter_label: &str,
+    visits: &[MedicalVisit],
+    records_without_visits: &[String],
+    generated_at: DateTime<Utc>,
+) -> String {
+    let mut out = format!(
+        "# Treatment Chronology: {matter_label}\n\nGenerated: {}\n\n",
+        generated_at.to_rfc3339()
+    );
+    if visits.is_empty() {
+        out.push_str("- No visits found in the matter's medical records.\n");
+    } else {
+        out.push_str(
+            "## Treating Providers\n\n| Provider | Facility | First Visit | Last Visit | Visits | Diagnoses |\n|---|---|---|---|---|---|\n",
+        );
+        for summary in provider_summaries(visits) {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} |\n",
+                table_cell(&summary.provider),
+                table_cell(&summary.facilities.join("; ")),
+                summary.first_visit,
+                summary.last_visit,
+                summary.visit_count,
+                table_cell(&diagnoses_cell(&summary.diagnoses)),
+            ));
+        }
+        out.push_str(
+            "\n## Visits\n\n| Date | Provider | Facility | Visit Type | Diagnoses | Source |\n|---|---|---|---|---|---|\n",
+        );
+        for visit in visits {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} |\n",
+                visit.date,
+                table_cell(visit.provider.as_deref().unwrap_or_default()),
+                table_cell(visit.facility.as_deref().unwrap_or_default()),
+                table_cell(visit.visit_type.as_deref().unwrap_or_default()),
+                table_cell(&diagnoses_cell(&visit.diagnoses)),
+                table_cell(&visit.citation()),
+            ));
+        }
+    }
+    if !records_without_visits.is_empty() {
+        out.push_str("\n## Records Needing Manual Review\n\nNo dated visits were found in:\n\n");
+        for path in records_without_visits {
+            out.push_str(&format!("- {path}\n"));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ER_RECORD: &str = "\
+St. Mary's Hospital Emergency Department
+Facility: St. Mary's Hospital
+Date of Service: 03/14/2024        Provider: Dr. Alice Chen
+Visit Type: Emergency
+Chief complaint: neck pain after rear-end collision.
+Page 1 of 3
+Date of Service: 03/14/2024
+Diagnoses:
+1. Cervical strain, initial encounter (S13.4XXA)
+2. Headache - R51.9
+
+Page 2 of 3
+**DOS:** March 28, 2024
+Attending: Dr. Alice Chen
+Assessment: Whiplash-associated disorder; Post-traumatic headache G44.309
+Page 3 of 3
+";
+
+    #[test]
+    fn footer_page_markers_and_repeated_headers_cite_pages() {
+        let visits = extract_visits("matters/demo/medical/records/er.md", ER_RECORD);
+        assert_eq!(visits.len(), 2, "{visits:#?}");
+
+        let first = &visits[0];
+        assert_eq!(first.date, NaiveDate::from_ymd_opt(2024, 3, 14).unwrap());
+        assert_eq!(first.provider.as_deref(), Some("Dr. Alice Chen"));
+        assert_eq!(first.facility.as_deref(), Some("St. Mary's Hospital"));
+        assert_eq!(first.visit_type.as_deref(), Some("Emergency"));
+        assert_eq!(
+            first.diagnoses,
+            vec![
+                Diagnosis {
+                    description: "Cervical strain, initial encounter".to_string(),
+                    icd10: Some("S13.4XXA".to_string()),
+                },
+                Diagnosis {
+                    description: "Headache".to_string(),
+                    icd10: Some("R51.9".to_string()),
+                },
+            ]
+        );
+        assert_eq!(first.citation(), "er.md pp. 1-2");
+
+        let second = &visits[1];
+        assert_eq!(second.date, NaiveDate::from_ymd_opt(2024, 3, 28).unwrap());
+        assert_eq!(second.diagnoses.len(), 2);
+        assert_eq!(second.diagnoses[1].icd10.as_deref(), Some("G44.309"));
+        assert_eq!(second.citation(), "er.md p. 3");
+    }
+
+    #[test]
+    fn form_feeds_split_pages_and_dates_parse_in_common_formats() {
+        let content = "Clinic: Lakeside Physiotherapy\nTherapist: J. Patel, PT\n\x0cVisit Date: 2024-04-02\nDx: Neck sprain\n\x0cVisit Date: 2024-04-09\nDx: Neck sprain\n";
+        let visits = extract_visits("matters/demo/medical/records/physio.md", content);
+        assert_eq!(visits.len(), 2);
+        assert_eq!(visits[0].citation(), "physio.md p. 2");
+        assert_eq!(visits[1].citation(), "physio.md p. 3");
+        assert_eq!(visits[1].provider.as_deref(), Some("J. Patel, PT"));
+
+        assert_eq!(
+            parse_record_date("14/03/2024"),
+            NaiveDate::from_ymd_opt(2024, 3, 14)
+        );
+        assert_eq!(
+            parse_record_date("2024/03/14"),
+            NaiveDate::from_ymd_opt(2024, 3, 14)
+        );
+        assert_eq!(parse_record_date("pending"), None);
+    }
+
+    #[test]
+    fn chronology_groups_visits_by_treating_provider() {
+        let mut visits = extract_visits("matters/demo/medical/records/er.md", ER_RECORD);
+        visits.extend(extract_visits(
+            "matters/demo/medical/records/physio.md",
+            "Clinic: Lakeside Physiotherapy\nVisit Date: 2024-04-02\nTherapist: J. Patel, PT\nDx: Neck sprain\n",
+        ));
+        sort_visits(&mut visits);
+        let summaries = provider_summaries(&visits);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].provider, "Dr. Alice Chen");
+        assert_eq!(summaries[0].visit_count, 2);
+        assert_eq!(
+            summaries[0].last_visit,
+            NaiveDate::from_ymd_opt(2024, 3, 28).unwrap()
+        );
+
+        let markdown = render_treatment_chronology(
+            "demo",
+            &visits,
+            &["matters/demo/medical/records/billing.md".to_string()],
+            Utc::now(),
+        );
+        assert!(
+            markdown
+                .contains("| Dr. Alice Chen | St. Mary's Hospital | 2024-03-14 | 2024-03-28 | 2 |")
+        );
+        assert!(markdown.contains(
+            "| 2024-04-02 | J. Patel, PT | Lakeside Physiotherapy |  | Neck sprain | physio.md p. 1 |"
+        ));
+        assert!(markdown.contains("- matters/demo/medical/records/billing.md"));
+    }
+}
@@ -0,0 +1,262 @@
+//! Filing chat exchanges into a matter as memo documents.
+//!
+//! Informal research done in chat can be preserved as work product: the
+//! selected exchange is rendered as a dated, attributed memo (see
+//! [`render_conversation_memo`]), written under the matter's `memos/`
+//! folder, and registered as an internal draft matter document. The
+//! gateway's `file-to-matter` action and the `/file` chat command both go
+//! through [`file_conversation_memo`].
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::db::{
+    CreateDocumentVersionParams, Database, DocumentReadinessState, MatterDocumentCategory,
+    MatterDocumentRecord, UpsertMatterDocumentParams,
+};
+use crate::error::Error;
+use crate::workspace::Workspace;
+
+/// Longest title derived from the first question.
+const MAX_DEFAULT_TITLE_CHARS: usize = 60;
+
+/// Longest slug used in memo file names.
+const MAX_SLUG_CHARS: usize = 48;
+
+/// One question and answer from a chat thread.
+#[derive(Debug, Clone)]
+pub struct MemoExchange {
+    pub asked_at: Option<DateTime<Utc>>,
+    pub question: String,
+    pub answer: String,
+}
+
+/// A chat exchange being filed into a matter.
+#[derive(Debug, Clone)]
+pub struct ConversationMemo {
+    pub matter_id: String,
+    pub title: String,
+    /// Person filing the memo; recorded as its author.
+    pub filed_by: String,
+    pub filed_at: DateTime<Utc>,
+    pub thread_id: Uuid,
+    /// Model that produced the answers, when known.
+    pub model: Option<String>,
+    pub exchanges: Vec<MemoExchange>,
+}
+
+/// Title derived from the first question: its first line, truncated.
+pub fn default_memo_title(exchanges: &[MemoExchange]) -> String {
+    let first_line = exchanges
+        .first()
+        .and_then(|e| e.question.lines().find(|line| !line.trim().is_empty()))
+        .map(str::trim)
+        .unwrap_or_default();
+    if first_line.is_empty() {
+        return "Research discussion".to_string();
+    }
+    if first_line.chars().count() > MAX_DEFAULT_TITLE_CHARS {
+        let truncated: String = first_line
+            .chars()
+            .take(MAX_DEFAULT_TITLE_CHARS - 3)
+            .collect();
+        format!("{}...", truncated.trim_end())
+    } else {
+        first_line.to_string()
+    }
+}
+
+/// Lowercase, hyphenated file-name slug for `title`.
+pub(crate) fn slug(title: &str) -> String {
+    let mut out = String::new();
+    for c in title.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+        if out.len() >= MAX_SLUG_CHARS {
+            break;
+        }
+    }
+    let out = out.trim_end_matches('-');
+    if out.is_empty() {
+        "memo".to_string()
+    } else {
+        out.to_string()
+    }
+}
+
+/// Workspace path for a memo filed at `filed_at`:
+/// `matters/acme/memos/2026-10-14-153000-lease-renewal-options.md`.
+pub fn memo_path(
+    matter_root: &str,
+    matter_id: &str,
+    filed_at: DateTime<Utc>,
+    title: &str,
+) -> String {
+    format!(
+        "{}/{matter_id}/memos/{}-{}.md",
+        matter_root.trim_matches('/'),
+        filed_at.format("%Y-%m-%d-%H%M%S"),
+        slug(title)
+    )
+}
+
+/// Render the memo document.
+pub fn render_conversation_memo(memo: &ConversationMemo) -> String {
+    let mut out = format!(
+        "# Memo: {}\n\n\
+         > **Privileged & confidential — attorney work product.** Filed from an AI-assisted \
+         research discussion; verify all authorities and conclusions before relying on them.\n\n\
+         - **Matter:** {}\n\
+         - **Filed by:** {}\n\
+         - **Date filed:** {}\n\
+         - **Source:** chat thread `{}`\n",
+        memo.title,
+        memo.matter_id,
+        memo.filed_by,
+        memo.filed_at.format("%Y-%m-%d %H:%M UTC"),
+        memo.thread_id,
+    );
+    if let Some(model) = memo.model.as_deref() {
+        out.push_str(&format!("- **Assistant model:** {model}\n"));
+    }
+    for (idx, exchange) in memo.exchanges.iter().enumerate() {
+        out.push_str(&format!("\n## Exchange {}", idx + 1));
+        if let Some(asked_at) = exchange.asked_at {
+            out.push_str(&format!(" ({})", asked_at.format("%Y-%m-%d %H:%M UTC")));
+        }
+        out.push_str(&format!(
+            "\n\n### Question ({})\n\n{}\n\n### Response (AI assistant)\n\n{}\n",
+            memo.filed_by,
+            exchange.question.trim(),
+            exchange.answer.trim()
+        ));
+    }
+    out
+}
+
+/// Write the memo into the matter and register it as a draft internal
+/// document with an initial version.
+pub async fn file_conversation_memo(
+    store: &dyn Database,
+    workspace: &Workspace,
+    user_id: &str,
+    matter_root: &str,
+    memo: &ConversationMemo,
+) -> Result<MatterDocumentRecord, Error> {
+    let path = memo_path(matter_root, &memo.matter_id, memo.filed_at, &memo.title);
+    file_memo_document(
+        store,
+        workspace,
+        user_id,
+        &memo.matter_id,
+        &path,
+        &memo.title,
+        &render_conversation_memo(memo),
+    )
+    .await
+}
+
+/// Write rendered memo `content` to `path` and register it as a draft
+/// internal matter document with an initial version.
+pub async fn file_memo_document(
+    store: &dyn Database,
+    workspace: &Workspace,
+    user_id: &str,
+    matter_id: &str,
+    path: &str,
+    title: &str,
+    content: &str,
+) -> Result<MatterDocumentRecord, Error> {
+    let written = workspace.write(path, content).await?;
+    let document = store
+        .upsert_matter_document(
+            user_id,
+            matter_id,
+            &UpsertMatterDocumentParams {
+                memory_document_id: written.id,
+                path: written.path.clone(),
+                display_name: format!("Memo: {title}"),
+                category: MatterDocumentCategory::Internal,
+                readiness_state: Some(DocumentReadinessState::Draft),
+            },
+        )
+        .await?;
+    store
+        .create_document_version(
+            user_id,
+            &CreateDocumentVersionParams {
+                matter_document_id: document.id,
+                label: "initial".to_string(),
+                memory_document_id: written.id,
+            },
+        )
+        .await?;
+    Ok(document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn memo() -> ConversationMemo {
+        ConversationMemo {
+            matter_id: "acme-v-foo".to_string(),
+            title: "Lease renewal options".to_string(),
+            filed_by: "Jane Attorney".to_string(),
+            filed_at: Utc.with_ymd_and_hms(2026, 10, 14, 15, 30, 0).unwrap(),
+            thread_id: Uuid::nil(),
+            model: Some("claude-sonnet".to_string()),
+            exchanges: vec![MemoExchange {
+                asked_at: Some(Utc.with_ymd_and_hms(2026, 10, 14, 15, 0, 0).unwrap()),
+                question: "Can the tenant renew early?".to_string(),
+                answer: "Section 4.2 allows renewal on 90 days' notice.".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn memo_path_is_dated_and_slugged() {
+        let memo = memo();
+        assert_eq!(
+            memo_path(
+                "/matters/",
+                &memo.matter_id,
+                memo.filed_at,
+                "Lease: renewal/options?"
+            ),
+            "matters/acme-v-foo/memos/2026-10-14-153000-lease-renewal-options.md"
+        );
+        assert!(memo_path("matters", "m", memo.filed_at, "¿¡!").ends_with("-memo.md"));
+    }
+
+    #[test]
+    fn default_title_uses_first_question_line() {
+        let mut exchanges = memo().exchanges;
+        assert_eq!(
+            default_memo_title(&exchanges),
+            "Can the tenant renew early?"
+        );
+        exchanges[0].question = format!("\n{}\nmore", "x".repeat(80));
+        let title = default_memo_title(&exchanges);
+        assert_eq!(title.chars().count(), MAX_DEFAULT_TITLE_CHARS);
+        assert!(title.ends_with("..."));
+        assert_eq!(default_memo_title(&[]), "Research discussion");
+    }
+
+    #[test]
+    fn rendered_memo_is_dated_and_attributed() {
+        let rendered = render_conversation_memo(&memo());
+        assert!(rendered.starts_with("# Memo: Lease renewal options\n"));
+        assert!(rendered.contains("attorney work product"));
+        assert!(rendered.contains("- **Filed by:** Jane Attorney"));
+        assert!(rendered.contains("- **Date filed:** 2026-10-14 15:30 UTC"));
+        assert!(rendered.contains("- **Assistant model:** claude-sonnet"));
+        assert!(rendered.contains("## Exchange 1 (2026-10-14 15:00 UTC)"));
+        assert!(rendered.contains("### Question (Jane Attorney)\n\nCan the tenant renew early?"));
+        assert!(rendered.contains("### Response (AI assistant)\n\nSection 4.2"));
+    }
+}
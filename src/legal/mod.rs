@@ -1,15 +1,51 @@
 //! Legal workflow helpers for cLawyer.
 
+pub mod anonymize;
 pub mod audit;
 pub mod backup;
+pub mod backup_schedule;
 pub mod billing;
+pub mod bulk_conflicts;
 pub mod calendar;
+pub mod caption;
+pub mod chronology;
 pub mod citations;
+pub mod classify;
+pub mod client_comms;
+pub mod closeout;
+pub mod compare;
+pub mod correspondence;
+pub mod custom_fields;
+pub mod damages;
+pub mod draft_check;
 pub mod docgen;
+pub mod email_intake;
+pub mod entities;
+pub mod exhibits;
+pub mod filing_validation;
+pub mod immigration;
+pub mod ip_docket;
 pub mod jurisdictions;
 pub mod ledes;
+pub mod locale;
+pub mod markdown;
 pub mod matter;
+pub mod medical_records;
+pub mod memo;
+pub mod notes;
 pub mod policy;
+pub mod precedent;
+pub mod readability;
+pub mod scaffold;
+pub mod settlement;
 pub mod skeptical;
+pub mod stages;
+pub mod status_memo;
+pub mod status_report;
+pub mod storage;
+pub mod timeline;
+pub mod transcript;
+pub mod translation;
 pub mod trust;
+pub mod watchlist;
 pub mod workspace_crypto;
@@ -0,0 +1,142 @@
+//! Collaborative matter notes: `@mention` parsing and participant lookup.
+//!
+//! Notes may mention matter participants as `@user_id`. The gateway pushes
+//! a `matter_note_mention` event for each mentioned participant when a note
+//! is created, and for newly added mentions when it is edited.
+
+use crate::db::Database;
+use crate::error::DatabaseError;
+
+/// Longest excerpt carried in a mention notification.
+const MAX_EXCERPT_CHARS: usize = 140;
+
+fn is_handle_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')
+}
+
+/// Lowercased, de-duplicated `@handles` in `body`, in order of appearance.
+///
+/// An `@` only starts a mention at the beginning of the text or after a
+/// character that cannot be part of a handle, so email addresses such as
+/// `jane@firm.com` are not treated as mentions.
+pub fn extract_mentions(body: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut chars = body.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        let starts_mention = c == '@' && !prev.is_some_and(|p| is_handle_char(p) || p == '@');
+        prev = Some(c);
+        if !starts_mention {
+            continue;
+        }
+        let rest = &body[idx + 1..];
+        let len = rest
+            .find(|ch: char| !is_handle_char(ch))
+            .unwrap_or(rest.len());
+        let handle = rest[..len]
+            .trim_end_matches(['.', '-'])
+            .to_ascii_lowercase();
+        if !handle.is_empty() && !mentions.contains(&handle) {
+            mentions.push(handle);
+        }
+        while let Some((_, ch)) = chars.next_if(|(_, ch)| is_handle_char(*ch)) {
+            prev = Some(ch);
+        }
+    }
+    mentions
+}
+
+/// Mentions in `new_body` that were not already present in `old_body`.
+pub fn new_mentions(old_body: &str, new_body: &str) -> Vec<String> {
+    let previous = extract_mentions(old_body);
+    extract_mentions(new_body)
+        .into_iter()
+        .filter(|handle| !previous.contains(handle))
+        .collect()
+}
+
+/// First line of a note, truncated for notifications.
+pub fn note_excerpt(body: &str) -> String {
+    let line = body
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    if line.chars().count() > MAX_EXCERPT_CHARS {
+        let truncated: String = line.chars().take(MAX_EXCERPT_CHARS - 3).collect();
+        format!("{}...", truncated.trim_end())
+    } else {
+        line.to_string()
+    }
+}
+
+/// Users who can be mentioned on a matter: its owner and every member.
+pub async fn matter_participants(
+    store: &dyn Database,
+    owner_user_id: &str,
+    matter_id: &str,
+) -> Result<Vec<String>, DatabaseError> {
+    let mut participants = vec![owner_user_id.to_string()];
+    for membership in store
+        .list_matter_memberships(owner_user_id, matter_id)
+        .await?
+    {
+        if !participants.contains(&membership.member_user_id) {
+            participants.push(membership.member_user_id);
+        }
+    }
+    Ok(participants)
+}
+
+/// Participants named by `mentions`, matched case-insensitively on user id.
+pub fn resolve_mentions(mentions: &[String], participants: &[String]) -> Vec<String> {
+    participants
+        .iter()
+        .filter(|user_id| mentions.contains(&user_id.to_ascii_lowercase()))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mentions_are_parsed_and_deduplicated() {
+        assert_eq!(
+            extract_mentions("@Alice please review; cc @bob.smith, @alice."),
+            vec!["alice".to_string(), "bob.smith".to_string()]
+        );
+        assert!(extract_mentions("email jane@firm.com or @@ or @ alone").is_empty());
+        assert_eq!(extract_mentions("(@carol)"), vec!["carol".to_string()]);
+    }
+
+    #[test]
+    fn only_added_mentions_are_new() {
+        assert_eq!(
+            new_mentions("@alice draft", "@alice @bob revised"),
+            vec!["bob".to_string()]
+        );
+        assert!(new_mentions("@alice", "@ALICE").is_empty());
+    }
+
+    #[test]
+    fn mentions_resolve_to_participants_only() {
+        let participants = vec!["default".to_string(), "Alice".to_string()];
+        assert_eq!(
+            resolve_mentions(&extract_mentions("@alice @mallory"), &participants),
+            vec!["Alice".to_string()]
+        );
+    }
+
+    #[test]
+    fn excerpt_uses_first_line() {
+        assert_eq!(
+            note_excerpt("\n  Call opposing counsel \nmore"),
+            "Call opposing counsel"
+        );
+        let excerpt = note_excerpt(&"x".repeat(200));
+        assert_eq!(excerpt.chars().count(), MAX_EXCERPT_CHARS);
+        assert!(excerpt.ends_with("..."));
+    }
+}
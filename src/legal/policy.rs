@@ -33,6 +33,18 @@ const PRIVILEGE_GUARD_CONFIDENTIAL_TOOLS: &[&str] = &[
     "memory_tree",
 ];
 
+/// Workspace folder holding matters when no legal config is attached.
+pub const DEFAULT_MATTER_ROOT: &str = "matters";
+
+/// Matter root from the legal config without surrounding slashes, or
+/// [`DEFAULT_MATTER_ROOT`] when there is no config or the root is blank.
+pub fn matter_root(legal: Option<&LegalConfig>) -> &str {
+    legal
+        .map(|legal| legal.matter_root.trim_matches('/'))
+        .filter(|root| !root.is_empty())
+        .unwrap_or(DEFAULT_MATTER_ROOT)
+}
+
 /// True when legal hardening is in max-lockdown mode.
 pub fn is_max_lockdown(config: &LegalConfig) -> bool {
     config.enabled && config.hardening == LegalHardeningProfile::MaxLockdown
@@ -189,6 +201,7 @@ mod tests {
             conflict_check_enabled: true,
             conflict_file_fallback_enabled: false,
             conflict_reindex_on_startup: false,
+            supervised_mode: false,
             network: LegalNetworkConfig {
                 deny_by_default: true,
                 allowed_domains: vec!["example.com".to_string()],
@@ -210,6 +223,8 @@ mod tests {
                 exclude_from_search: true,
                 require_master_key_in_max_lockdown: true,
             },
+            storage: crate::config::LegalStorageConfig::default(),
+            llm_routing: crate::config::LegalLlmRoutingConfig::default(),
         }
     }
 
@@ -221,6 +236,16 @@ mod tests {
         assert!(!is_network_domain_allowed(&cfg, "example.org"));
     }
 
+    #[test]
+    fn matter_root_trims_slashes_and_falls_back_to_default() {
+        let mut cfg = legal_for_test();
+        cfg.matter_root = "/cases/".to_string();
+        assert_eq!(matter_root(Some(&cfg)), "cases");
+        cfg.matter_root = "/".to_string();
+        assert_eq!(matter_root(Some(&cfg)), DEFAULT_MATTER_ROOT);
+        assert_eq!(matter_root(None), DEFAULT_MATTER_ROOT);
+    }
+
     #[test]
     fn max_lockdown_still_forces_side_effect_approval() {
         let mut cfg = legal_for_test();
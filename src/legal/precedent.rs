@@ -0,0 +1,439 @@
+//! Firm precedent bank.
+//!
+//! Work product from closed matters is promoted into a firm-wide collection
+//! under `precedent/`: client details are replaced with placeholders (see
+//! [`apply_replacements`]), the copy is written without any reference to its
+//! source matter, and a [`PrecedentRecord`] keeps the attribution. Retrieval
+//! ([`suggest_precedents`]) runs a hybrid search over the collection, scores
+//! each hit by how much of the query it covers, and drops precedent whose
+//! source matter the requester cannot access, so an ethical wall around a
+//! matter also screens the precedent drawn from it.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::{CreatePrecedentParams, Database, MatterRecord, PrecedentRecord};
+use crate::error::Error;
+use crate::workspace::{SearchConfig, Workspace};
+
+/// Workspace folder holding the precedent collection.
+pub const PRECEDENT_ROOT: &str = "precedent";
+
+/// Placeholder for the source matter's client name.
+pub const CLIENT_PLACEHOLDER: &str = "[Client]";
+
+/// Candidate chunks fetched before grouping and screening.
+const SEARCH_CANDIDATES: usize = 50;
+
+/// Longest excerpt returned with a suggestion.
+const EXCERPT_CHARS: usize = 240;
+
+/// Words too common to count toward similarity.
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "be", "by", "for", "from", "in", "is", "of", "on", "or", "that",
+    "the", "this", "to", "with",
+];
+
+/// One text substitution applied when promoting a document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Replacement {
+    /// Text in the source document, matched case-insensitively on word
+    /// boundaries.
+    pub original: String,
+    /// Text written in its place, e.g. `[Client]`.
+    pub placeholder: String,
+}
+
+/// Replacements applied to every promotion: the client's name.
+pub fn default_replacements(client_name: Option<&str>) -> Vec<Replacement> {
+    client_name
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| Replacement {
+            original: name.to_string(),
+            placeholder: CLIENT_PLACEHOLDER.to_string(),
+        })
+        .into_iter()
+        .collect()
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Replace whole-word, case-insensitive occurrences of `original`.
+fn replace_term(text: &str, original: &str, placeholder: &str) -> (String, usize) {
+    let Ok(re) = RegexBuilder::new(&regex::escape(original))
+        .case_insensitive(true)
+        .build()
+    else {
+        return (text.to_string(), 0);
+    };
+    let starts_word = original.chars().next().is_some_and(is_word_char);
+    let ends_word = original.chars().last().is_some_and(is_word_char);
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    let mut count = 0;
+    for m in re.find_iter(text) {
+        let before = text[..m.start()].chars().next_back();
+        let after = text[m.end()..].chars().next();
+        if (starts_word && before.is_some_and(is_word_char))
+            || (ends_word && after.is_some_and(is_word_char))
+        {
+            continue;
+        }
+        out.push_str(&text[last..m.start()]);
+        out.push_str(placeholder);
+        last = m.end();
+        count += 1;
+    }
+    out.push_str(&text[last..]);
+    (out, count)
+}
+
+/// Apply `replacements`, longest original first so `Acme Corporation` is
+/// replaced before `Acme`. Returns the new text and the number of
+/// substitutions made.
+pub fn apply_replacements(text: &str, replacements: &[Replacement]) -> (String, usize) {
+    let mut ordered: Vec<&Replacement> = replacements
+        .iter()
+        .filter(|r| !r.original.trim().is_empty())
+        .collect();
+    ordered.sort_by_key(|r| std::cmp::Reverse(r.original.trim().chars().count()));
+    let mut out = text.to_string();
+    let mut total = 0;
+    for replacement in ordered {
+        let (next, count) =
+            replace_term(&out, replacement.original.trim(), &replacement.placeholder);
+        out = next;
+        total += count;
+    }
+    (out, total)
+}
+
+/// Workspace path for a new precedent: `precedent/share-purchase-agreement-1a2b3c4d.md`.
+pub fn precedent_path(title: &str, id: Uuid) -> String {
+    let id = id.simple().to_string();
+    format!(
+        "{PRECEDENT_ROOT}/{}-{}.md",
+        crate::legal::memo::slug(title),
+        &id[..8]
+    )
+}
+
+/// Text after a leading YAML front-matter block, if any.
+fn strip_front_matter(content: &str) -> &str {
+    let Some(rest) = content.strip_prefix("---\n") else {
+        return content;
+    };
+    match rest.find("\n---\n") {
+        Some(end) => &rest[end + 5..],
+        None => content,
+    }
+}
+
+/// Render the precedent file. It names the document type, practice area,
+/// and jurisdiction but never the source matter.
+pub fn render_precedent(
+    title: &str,
+    document_type: Option<&str>,
+    matter: &MatterRecord,
+    body: &str,
+) -> String {
+    let mut out = format!(
+        "# Precedent: {title}\n\n\
+         > Firm precedent from closed-matter work product. Client details were replaced \
+         with placeholders; adapt every term to the new matter before use.\n\n"
+    );
+    for (label, value) in [
+        ("Document type", document_type),
+        ("Practice area", matter.practice_area.as_deref()),
+        ("Jurisdiction", matter.jurisdiction.as_deref()),
+    ] {
+        if let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) {
+            out.push_str(&format!("- **{label}:** {value}\n"));
+        }
+    }
+    out.push_str("\n---\n\n");
+    out.push_str(strip_front_matter(body).trim());
+    out.push('\n');
+    out
+}
+
+/// A document being promoted from a closed matter.
+#[derive(Debug, Clone)]
+pub struct PrecedentPromotion<'a> {
+    pub matter: &'a MatterRecord,
+    pub source_path: &'a str,
+    pub source_content: &'a str,
+    pub title: &'a str,
+    pub document_type: Option<&'a str>,
+    pub replacements: &'a [Replacement],
+    pub promoted_by: &'a str,
+}
+
+/// Write the anonymized copy under `precedent/` and record it. Returns the
+/// record and the number of substitutions made.
+pub async fn promote_precedent(
+    store: &dyn Database,
+    workspace: &Workspace,
+    user_id: &str,
+    promotion: &PrecedentPromotion<'_>,
+) -> Result<(PrecedentRecord, usize), Error> {
+    let (body, substitutions) =
+        apply_replacements(promotion.source_content, promotion.replacements);
+    let path = precedent_path(promotion.title, Uuid::new_v4());
+    let content = render_precedent(
+        promotion.title,
+        promotion.document_type,
+        promotion.matter,
+        &body,
+    );
+    workspace.write(&path, &content).await?;
+    let record = store
+        .create_precedent(
+            user_id,
+            &CreatePrecedentParams {
+                title: promotion.title.to_string(),
+                path,
+                source_matter_id: promotion.matter.matter_id.clone(),
+                source_path: promotion.source_path.to_string(),
+                document_type: promotion.document_type.map(str::to_string),
+                practice_area: promotion.matter.practice_area.clone(),
+                jurisdiction: promotion.matter.jurisdiction.clone(),
+                promoted_by: promotion.promoted_by.to_string(),
+            },
+        )
+        .await?;
+    Ok((record, substitutions))
+}
+
+/// Precedent the requester may see: those whose source matter they can
+/// access. Matter owners see everything.
+pub async fn visible_precedents(
+    store: &dyn Database,
+    user_id: &str,
+    requesting_user_id: &str,
+) -> Result<Vec<PrecedentRecord>, Error> {
+    let precedents = store.list_precedents(user_id).await?;
+    let mut access: HashMap<String, bool> = HashMap::new();
+    let mut visible = Vec::with_capacity(precedents.len());
+    for precedent in precedents {
+        let allowed = match access.get(&precedent.source_matter_id) {
+            Some(allowed) => *allowed,
+            None => {
+                let allowed = store
+                    .check_matter_access(user_id, &precedent.source_matter_id, requesting_user_id)
+                    .await?
+                    .is_some();
+                access.insert(precedent.source_matter_id.clone(), allowed);
+                allowed
+            }
+        };
+        if allowed {
+            visible.push(precedent);
+        }
+    }
+    Ok(visible)
+}
+
+/// Lowercased query terms with stop words and duplicates removed.
+pub fn query_terms(query: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|term| term.chars().count() > 1 && !STOP_WORDS.contains(&term.as_str()))
+        .filter(|term| seen.insert(term.clone()))
+        .collect()
+}
+
+/// Share of `terms` that appear as words in `text`, from 0.0 to 1.0.
+pub fn similarity(terms: &[String], text: &str) -> f64 {
+    if terms.is_empty() {
+        return 0.0;
+    }
+    let words: HashSet<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let matched = terms.iter().filter(|term| words.contains(*term)).count();
+    matched as f64 / terms.len() as f64
+}
+
+/// Up to [`EXCERPT_CHARS`] of `text`, starting at the first paragraph that
+/// mentions a query term so the precedent header does not crowd it out.
+fn excerpt(terms: &[String], text: &str) -> String {
+    let text = text.trim();
+    let text = text
+        .split("\n\n")
+        .find(|paragraph| similarity(terms, paragraph) > 0.0)
+        .and_then(|paragraph| text.find(paragraph).map(|start| &text[start..]))
+        .unwrap_or(text);
+    let mut out: String = text.chars().take(EXCERPT_CHARS).collect();
+    if text.chars().count() > EXCERPT_CHARS {
+        out.push('…');
+    }
+    out
+}
+
+/// A precedent proposed for reuse, with its source-matter attribution.
+#[derive(Debug, Clone, Serialize)]
+pub struct PrecedentSuggestion {
+    pub precedent_id: Uuid,
+    pub title: String,
+    pub path: String,
+    /// Share of the query terms found in the best-matching passage.
+    pub similarity: f64,
+    /// Best-matching passage.
+    pub excerpt: String,
+    pub source_matter_id: String,
+    pub source_path: String,
+    pub document_type: Option<String>,
+    pub practice_area: Option<String>,
+    pub jurisdiction: Option<String>,
+    /// Whether the precedent comes from the drafting matter's practice area.
+    pub same_practice_area: bool,
+    pub promoted_at: DateTime<Utc>,
+}
+
+/// Search the precedent collection for `query` on behalf of
+/// `requesting_user_id`, best match first. Precedent behind an ethical wall
+/// (no access to the source matter) is never returned. Ties go to
+/// precedent from `practice_area`.
+pub async fn suggest_precedents(
+    store: &dyn Database,
+    workspace: &Workspace,
+    user_id: &str,
+    requesting_user_id: &str,
+    query: &str,
+    practice_area: Option<&str>,
+    limit: usize,
+) -> Result<Vec<PrecedentSuggestion>, Error> {
+    let terms = query_terms(query);
+    if terms.is_empty() || limit == 0 {
+        return Ok(Vec::new());
+    }
+    let by_path: HashMap<String, PrecedentRecord> =
+        visible_precedents(store, user_id, requesting_user_id)
+            .await?
+            .into_iter()
+            .map(|precedent| (precedent.path.clone(), precedent))
+            .collect();
+    if by_path.is_empty() {
+        return Ok(Vec::new());
+    }
+    let results = workspace
+        .search_with_config(
+            &terms.join(" "),
+            SearchConfig::default()
+                .with_limit(SEARCH_CANDIDATES)
+                .with_path_prefix(PRECEDENT_ROOT),
+        )
+        .await?;
+
+    let mut best: HashMap<String, (f64, String)> = HashMap::new();
+    for result in results {
+        if !by_path.contains_key(&result.path) {
+            continue;
+        }
+        let score = similarity(&terms, &result.content);
+        let entry = best
+            .entry(result.path.clone())
+            .or_insert((-1.0, String::new()));
+        if score > entry.0 {
+            *entry = (score, result.content);
+        }
+    }
+
+    let practice_area = practice_area.map(str::trim).filter(|p| !p.is_empty());
+    let mut suggestions: Vec<PrecedentSuggestion> = best
+        .into_iter()
+        .filter_map(|(path, (score, content))| {
+            let precedent = by_path.get(&path)?;
+            Some(PrecedentSuggestion {
+                precedent_id: precedent.id,
+                title: precedent.title.clone(),
+                path,
+                similarity: score.max(0.0),
+                excerpt: excerpt(&terms, &content),
+                source_matter_id: precedent.source_matter_id.clone(),
+                source_path: precedent.source_path.clone(),
+                document_type: precedent.document_type.clone(),
+                practice_area: precedent.practice_area.clone(),
+                jurisdiction: precedent.jurisdiction.clone(),
+                same_practice_area: practice_area.is_some_and(|area| {
+                    precedent
+                        .practice_area
+                        .as_deref()
+                        .is_some_and(|p| p.eq_ignore_ascii_case(area))
+                }),
+                promoted_at: precedent.created_at,
+            })
+        })
+        .collect();
+    suggestions.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then(b.same_practice_area.cmp(&a.same_practice_area))
+            .then(b.promoted_at.cmp(&a.promoted_at))
+    });
+    suggestions.truncate(limit);
+    Ok(suggestions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replacements_match_whole_words_longest_first() {
+        let replacements = vec![
+            Replacement {
+                original: "Acme".to_string(),
+                placeholder: "[Client]".to_string(),
+            },
+            Replacement {
+                original: "Acme Corporation".to_string(),
+                placeholder: "[Client]".to_string(),
+            },
+            Replacement {
+                original: "$4,250,000".to_string(),
+                placeholder: "[Purchase Price]".to_string(),
+            },
+        ];
+        let (out, count) = apply_replacements(
+            "ACME CORPORATION (\"Acme\") pays $4,250,000. Acmeville is unaffected.",
+            &replacements,
+        );
+        assert_eq!(
+            out,
+            "[Client] (\"[Client]\") pays [Purchase Price]. Acmeville is unaffected."
+        );
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn similarity_counts_query_terms_covered() {
+        let terms = query_terms("The indemnification cap for a software licence");
+        assert_eq!(terms, vec!["indemnification", "cap", "software", "licence"]);
+        assert_eq!(
+            similarity(&terms, "Indemnification is subject to the Cap."),
+            0.5
+        );
+        assert_eq!(similarity(&[], "anything"), 0.0);
+    }
+
+    #[test]
+    fn excerpt_starts_at_the_first_matching_paragraph() {
+        let terms = query_terms("indemnification cap");
+        let text = "# Precedent: SPA\n\nIntro.\n\n8. Indemnification capped at 10%.\n";
+        assert_eq!(excerpt(&terms, text), "8. Indemnification capped at 10%.");
+        assert_eq!(excerpt(&query_terms("escrow"), text), text.trim());
+    }
+}
@@ -0,0 +1,588 @@
+//! Readability and plain-language scoring for client-facing documents.
+//!
+//! [`score_document`] splits a document into passages (paragraphs and list
+//! blocks), computes Flesch reading ease and Flesch-Kincaid grade level for
+//! the whole document and for each passage, and finds legalese with a plain
+//! alternative. A passage is flagged when its grade level or its legalese
+//! density exceeds the firm's [`PlainLanguageThresholds`], stored in the
+//! `plain_language` setting. [`rewrite_passage`] asks the LLM for a
+//! plain-language version of a flagged passage.
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::db::Database;
+use crate::error::LlmError;
+use crate::llm::{ChatMessage, CompletionRequest, LlmProvider, LlmTask};
+
+/// Setting key for the firm's plain-language thresholds.
+pub const PLAIN_LANGUAGE_SETTING_KEY: &str = "plain_language";
+
+/// Default highest acceptable Flesch-Kincaid grade for client documents.
+pub const DEFAULT_MAX_GRADE: f64 = 10.0;
+/// Default highest acceptable legalese phrases per 100 words.
+pub const DEFAULT_MAX_LEGALESE_PER_100_WORDS: f64 = 1.0;
+
+/// Passages shorter than this are too short for a stable grade level and
+/// are only checked for legalese.
+const MIN_GRADED_WORDS: usize = 12;
+/// Longest passage text kept on a flagged passage.
+const MAX_PASSAGE_CHARS: usize = 2_000;
+
+/// Legalese phrases and their plain alternatives, longest phrases first.
+const LEGALESE: [(&str, &str); 40] = [
+    ("at this point in time", "now"),
+    ("in the event that", "if"),
+    ("until such time as", "until"),
+    ("for the purpose of", "to / for"),
+    ("in accordance with", "under"),
+    ("enclosed herewith", "enclosed"),
+    ("is in receipt of", "has received"),
+    ("with respect to", "about"),
+    ("notwithstanding", "despite"),
+    ("the undersigned", "I / we"),
+    ("aforementioned", "this / that"),
+    ("subsequent to", "after"),
+    ("hereinafter", "from now on / called"),
+    ("effectuate", "carry out"),
+    ("pursuant to", "under"),
+    ("heretofore", "until now"),
+    ("by virtue of", "because of"),
+    ("in lieu of", "instead of"),
+    ("forthwith", "immediately"),
+    ("inter alia", "among other things"),
+    ("witnesseth", "(omit)"),
+    ("hereunder", "under this"),
+    ("aforesaid", "this / that"),
+    ("thereafter", "after that"),
+    ("henceforth", "from now on"),
+    ("per annum", "a year"),
+    ("prior to", "before"),
+    ("commence", "start"),
+    ("endeavor", "try"),
+    ("endeavour", "try"),
+    ("utilize", "use"),
+    ("herewith", "with this"),
+    ("thereof", "of it"),
+    ("therein", "in it"),
+    ("whereby", "by which"),
+    ("whereas", "because / while"),
+    ("herein", "here / in this document"),
+    ("hereby", "(omit)"),
+    ("remit", "send / pay"),
+    ("deem", "consider / treat as"),
+];
+
+/// Abbreviations whose trailing period does not end a sentence.
+const ABBREVIATIONS: [&str; 20] = [
+    "mr.", "mrs.", "ms.", "dr.", "prof.", "st.", "no.", "nos.", "inc.", "corp.", "co.", "ltd.",
+    "llc.", "v.", "vs.", "e.g.", "i.e.", "etc.", "art.", "s.",
+];
+
+static LEGALESE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    let alternatives = LEGALESE
+        .iter()
+        .map(|(phrase, _)| phrase.replace(' ', r"\s+"))
+        .collect::<Vec<_>>()
+        .join("|");
+    Regex::new(&format!(r"(?i)\b(?:{alternatives})\b")).expect("valid legalese regex")
+});
+
+static LIST_ITEM_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(?:[-*+]|\d+[.)]|\([a-z0-9]+\))\s+").expect("valid list item regex")
+});
+
+static LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]*)\]\([^)]*\)").expect("valid markdown link regex"));
+
+/// Firm thresholds for plain-language review.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlainLanguageThresholds {
+    /// Highest acceptable Flesch-Kincaid grade level.
+    pub max_grade: f64,
+    /// Highest acceptable legalese phrases per 100 words.
+    pub max_legalese_per_100_words: f64,
+}
+
+impl Default for PlainLanguageThresholds {
+    fn default() -> Self {
+        Self {
+            max_grade: DEFAULT_MAX_GRADE,
+            max_legalese_per_100_words: DEFAULT_MAX_LEGALESE_PER_100_WORDS,
+        }
+    }
+}
+
+impl PlainLanguageThresholds {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1.0..=20.0).contains(&self.max_grade) {
+            return Err("max_grade must be between 1 and 20".to_string());
+        }
+        if !(0.0..=100.0).contains(&self.max_legalese_per_100_words) {
+            return Err("max_legalese_per_100_words must be between 0 and 100".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Parse and validate a `plain_language` setting value.
+pub fn parse_setting_value(value: &serde_json::Value) -> Result<PlainLanguageThresholds, String> {
+    let thresholds: PlainLanguageThresholds = serde_json::from_value(value.clone())
+        .map_err(|e| format!("invalid plain language thresholds: {e}"))?;
+    thresholds.validate()?;
+    Ok(thresholds)
+}
+
+/// The firm's thresholds from settings; defaults when the setting is absent
+/// or unreadable.
+pub async fn resolve_thresholds(
+    store: Option<&Arc<dyn Database>>,
+    user_id: &str,
+) -> PlainLanguageThresholds {
+    let Some(store) = store else {
+        return PlainLanguageThresholds::default();
+    };
+    match store.get_setting(user_id, PLAIN_LANGUAGE_SETTING_KEY).await {
+        Ok(Some(value)) => match parse_setting_value(&value) {
+            Ok(thresholds) => thresholds,
+            Err(err) => {
+                tracing::warn!(
+                    user_id,
+                    "Ignoring invalid {PLAIN_LANGUAGE_SETTING_KEY} setting: {err}"
+                );
+                PlainLanguageThresholds::default()
+            }
+        },
+        Ok(None) => PlainLanguageThresholds::default(),
+        Err(err) => {
+            tracing::warn!(
+                user_id,
+                "Failed to read {PLAIN_LANGUAGE_SETTING_KEY} setting: {err}"
+            );
+            PlainLanguageThresholds::default()
+        }
+    }
+}
+
+/// Readability metrics for a document or passage.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+pub struct ReadabilityMetrics {
+    pub words: usize,
+    pub sentences: usize,
+    pub syllables: usize,
+    /// Words of three or more syllables.
+    pub long_words: usize,
+    pub avg_sentence_words: f64,
+    /// Flesch reading ease (higher is easier; 60-70 is plain English).
+    pub reading_ease: f64,
+    /// Flesch-Kincaid grade level.
+    pub grade_level: f64,
+    pub legalese: usize,
+    pub legalese_per_100_words: f64,
+}
+
+/// A legalese phrase found in the text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LegaleseHit {
+    /// The phrase as written.
+    pub phrase: String,
+    pub plain: &'static str,
+    /// 1-based line.
+    pub line: usize,
+}
+
+/// A passage over one of the thresholds.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FlaggedPassage {
+    /// 1-based first line of the passage.
+    pub line: usize,
+    pub text: String,
+    pub metrics: ReadabilityMetrics,
+    pub legalese: Vec<LegaleseHit>,
+    /// Why the passage was flagged.
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReadabilityReport {
+    pub metrics: ReadabilityMetrics,
+    pub thresholds: PlainLanguageThresholds,
+    /// Whether the document as a whole is within the thresholds.
+    pub within_thresholds: bool,
+    pub passages: usize,
+    pub flagged: Vec<FlaggedPassage>,
+    /// Distinct legalese phrases, most frequent first.
+    pub legalese_summary: Vec<LegaleseCount>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LegaleseCount {
+    /// The phrase in lower case.
+    pub phrase: String,
+    pub plain: &'static str,
+    pub count: usize,
+}
+
+/// A passage of prose with its first line number.
+struct Passage {
+    line: usize,
+    text: String,
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Strip markdown syntax that would otherwise count as words or sentence
+/// breaks.
+fn plain_text(line: &str) -> String {
+    let line = line.trim_start_matches(['#', '>', ' ']);
+    let line = LINK_RE.replace_all(line, "$1");
+    line.replace("**", "").replace("__", "").replace('`', "")
+}
+
+/// Split a markdown document into prose passages, skipping front matter,
+/// headings, fenced code, and table rows.
+fn passages(text: &str) -> Vec<Passage> {
+    let mut out = Vec::new();
+    let mut current: Option<Passage> = None;
+    let mut lines = text.lines().enumerate().peekable();
+    if lines.peek().is_some_and(|(_, line)| line.trim() == "---") {
+        lines.next();
+        for (_, line) in lines.by_ref() {
+            if line.trim() == "---" {
+                break;
+            }
+        }
+    }
+    let mut in_fence = false;
+    for (idx, line) in lines {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_fence = !in_fence;
+            out.extend(current.take());
+            continue;
+        }
+        if in_fence
+            || trimmed.is_empty()
+            || trimmed.starts_with('#')
+            || trimmed.starts_with('|')
+            || trimmed.chars().all(|c| matches!(c, '-' | '*' | '_' | ' '))
+        {
+            out.extend(current.take());
+            continue;
+        }
+        let text = plain_text(line);
+        match current.as_mut() {
+            Some(passage) => {
+                passage.text.push('\n');
+                passage.text.push_str(&text);
+            }
+            None => {
+                current = Some(Passage {
+                    line: idx + 1,
+                    text,
+                })
+            }
+        }
+    }
+    out.extend(current);
+    out
+}
+
+/// Estimate syllables in an English word from its vowel groups.
+fn syllables(word: &str) -> usize {
+    let word: Vec<char> = word
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    if word.is_empty() {
+        return 0;
+    }
+    if word.len() <= 3 {
+        return 1;
+    }
+    let is_vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let mut count = 0;
+    let mut previous_vowel = false;
+    for &c in &word {
+        let vowel = is_vowel(c);
+        if vowel && !previous_vowel {
+            count += 1;
+        }
+        previous_vowel = vowel;
+    }
+    // Silent final "e" ("notice", "settle" keeps its "le" syllable).
+    let n = word.len();
+    if word[n - 1] == 'e' && (word[n - 2] != 'l' || is_vowel(word[n - 3])) && count > 1 {
+        count -= 1;
+    }
+    // "-ed" is silent unless it follows "t" or "d" ("signed" vs "dated").
+    if word.ends_with(&['e', 'd']) && !matches!(word[n - 3], 't' | 'd') && count > 1 {
+        count -= 1;
+    }
+    count.max(1)
+}
+
+fn ends_sentence(token: &str) -> bool {
+    let token = token.trim_end_matches(['"', '\'', ')', ']', '”', '’']);
+    if token.ends_with(['!', '?']) {
+        return true;
+    }
+    token.ends_with('.') && !ABBREVIATIONS.contains(&token.to_lowercase().as_str())
+}
+
+fn legalese_hits(text: &str, first_line: usize) -> Vec<LegaleseHit> {
+    let plain: HashMap<&str, &str> = LEGALESE.iter().copied().collect();
+    LEGALESE_RE
+        .find_iter(text)
+        .map(|found| {
+            let phrase = found
+                .as_str()
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            LegaleseHit {
+                plain: plain
+                    .get(phrase.to_lowercase().as_str())
+                    .copied()
+                    .unwrap_or_default(),
+                line: first_line + text[..found.start()].matches('\n').count(),
+                phrase,
+            }
+        })
+        .collect()
+}
+
+/// Compute readability metrics for plain text. List items without closing
+/// punctuation each count as a sentence.
+pub fn measure(text: &str) -> ReadabilityMetrics {
+    let mut metrics = ReadabilityMetrics::default();
+    for line in text.lines() {
+        let mut open_sentence = false;
+        for token in line.split_whitespace() {
+            if !token.chars().any(char::is_alphanumeric) {
+                continue;
+            }
+            metrics.words += 1;
+            let word_syllables = syllables(token).max(1);
+            metrics.syllables += word_syllables;
+            if word_syllables >= 3 {
+                metrics.long_words += 1;
+            }
+            open_sentence = true;
+            if ends_sentence(token) {
+                metrics.sentences += 1;
+                open_sentence = false;
+            }
+        }
+        if open_sentence && (LIST_ITEM_RE.is_match(line) || line.trim_end().ends_with(':')) {
+            metrics.sentences += 1;
+        }
+    }
+    if metrics.words > 0 && metrics.sentences == 0 {
+        metrics.sentences = 1;
+    }
+    metrics.legalese = LEGALESE_RE.find_iter(text).count();
+    if metrics.words == 0 {
+        return metrics;
+    }
+    let words = metrics.words as f64;
+    let words_per_sentence = words / metrics.sentences as f64;
+    let syllables_per_word = metrics.syllables as f64 / words;
+    metrics.avg_sentence_words = round1(words_per_sentence);
+    metrics.reading_ease = round1(206.835 - 1.015 * words_per_sentence - 84.6 * syllables_per_word);
+    metrics.grade_level = round1(0.39 * words_per_sentence + 11.8 * syllables_per_word - 15.59);
+    metrics.legalese_per_100_words = round1(metrics.legalese as f64 * 100.0 / words);
+    metrics
+}
+
+fn threshold_reasons(
+    metrics: &ReadabilityMetrics,
+    thresholds: &PlainLanguageThresholds,
+) -> Vec<String> {
+    let mut reasons = Vec::new();
+    if metrics.words >= MIN_GRADED_WORDS && metrics.grade_level > thresholds.max_grade {
+        reasons.push(format!(
+            "grade level {} is above {}",
+            metrics.grade_level, thresholds.max_grade
+        ));
+    }
+    if metrics.legalese > 0
+        && metrics.legalese_per_100_words > thresholds.max_legalese_per_100_words
+    {
+        reasons.push(format!(
+            "{} legalese phrase(s), {} per 100 words (limit {})",
+            metrics.legalese, metrics.legalese_per_100_words, thresholds.max_legalese_per_100_words
+        ));
+    }
+    reasons
+}
+
+/// Score a markdown document against the thresholds.
+pub fn score_document(text: &str, thresholds: &PlainLanguageThresholds) -> ReadabilityReport {
+    let passages = passages(text);
+    let prose = passages
+        .iter()
+        .map(|passage| passage.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let metrics = measure(&prose);
+
+    let mut summary: Vec<LegaleseCount> = Vec::new();
+    let mut flagged = Vec::new();
+    for passage in &passages {
+        let passage_metrics = measure(&passage.text);
+        let legalese = legalese_hits(&passage.text, passage.line);
+        for hit in &legalese {
+            let phrase = hit.phrase.to_lowercase();
+            match summary.iter_mut().find(|seen| seen.phrase == phrase) {
+                Some(seen) => seen.count += 1,
+                None => summary.push(LegaleseCount {
+                    phrase,
+                    plain: hit.plain,
+                    count: 1,
+                }),
+            }
+        }
+        let reasons = threshold_reasons(&passage_metrics, thresholds);
+        if reasons.is_empty() {
+            continue;
+        }
+        flagged.push(FlaggedPassage {
+            line: passage.line,
+            text: passage.text.chars().take(MAX_PASSAGE_CHARS).collect(),
+            metrics: passage_metrics,
+            legalese,
+            reasons,
+        });
+    }
+    summary.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.phrase.cmp(&b.phrase)));
+
+    ReadabilityReport {
+        within_thresholds: threshold_reasons(&metrics, thresholds).is_empty(),
+        metrics,
+        thresholds: *thresholds,
+        passages: passages.len(),
+        flagged,
+        legalese_summary: summary,
+    }
+}
+
+fn rewrite_prompt(passage: &FlaggedPassage, max_grade: f64) -> String {
+    let mut replacements = String::new();
+    for hit in &passage.legalese {
+        replacements.push_str(&format!("- \"{}\" -> {}\n", hit.phrase, hit.plain));
+    }
+    if replacements.is_empty() {
+        replacements.push_str("- none\n");
+    }
+    format!(
+        "Rewrite the following passage from a letter to a client in plain English.\n\
+         Aim for a grade level of {max_grade} or lower: short sentences, everyday words, \
+         active voice, and \"you\" for the client. Keep every fact, date, amount, name, \
+         deadline, defined term, and legal consequence; do not add advice or content. Replace \
+         legalese with plain alternatives:\n{replacements}\
+         Return only the rewritten passage.\n\n---\n{}\n---",
+        passage.text
+    )
+}
+
+/// Ask the LLM for a plain-language version of a flagged passage.
+pub async fn rewrite_passage(
+    llm: &dyn LlmProvider,
+    passage: &FlaggedPassage,
+    max_grade: f64,
+) -> Result<String, LlmError> {
+    let request =
+        CompletionRequest::new(vec![ChatMessage::user(rewrite_prompt(passage, max_grade))])
+            .with_temperature(0.2)
+            .with_task(LlmTask::Drafting);
+    let response = llm.complete(request).await?;
+    Ok(response.content.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics_follow_flesch_formulas() {
+        assert_eq!(syllables("notice"), 2);
+        assert_eq!(syllables("settle"), 2);
+        assert_eq!(syllables("signed"), 1);
+        assert_eq!(syllables("dated"), 2);
+        assert_eq!(syllables("agreement"), 3);
+
+        let metrics = measure("The cat sat on the mat. Mr. Smith saw it.");
+        assert_eq!(metrics.words, 10);
+        assert_eq!(metrics.sentences, 2);
+        assert_eq!(metrics.avg_sentence_words, 5.0);
+        assert!(metrics.grade_level < 2.0, "{metrics:?}");
+        assert!(metrics.reading_ease > 90.0, "{metrics:?}");
+
+        let list = measure("Bring:\n- your ID\n- the lease\n");
+        assert_eq!(list.sentences, 3);
+    }
+
+    #[test]
+    fn flags_dense_and_legalistic_passages() {
+        let letter = "\
+---
+title: Update
+---
+
+# Your case
+
+We filed your claim today. The court will set a hearing date soon.
+
+Pursuant to the aforementioned agreement, and notwithstanding any prior
+correspondence, the undersigned hereby requests remittance of the outstanding
+consideration forthwith, in accordance with the provisions thereof.
+
+```
+pursuant to code blocks are ignored
+```
+";
+        let report = score_document(letter, &PlainLanguageThresholds::default());
+        assert_eq!(report.passages, 2);
+        assert_eq!(report.flagged.len(), 1);
+        let flagged = &report.flagged[0];
+        assert_eq!(flagged.line, 9);
+        assert_eq!(flagged.reasons.len(), 2, "{:?}", flagged.reasons);
+        assert_eq!(flagged.legalese.len(), 8);
+        assert_eq!(flagged.legalese[0].phrase, "Pursuant to");
+        assert_eq!(flagged.legalese[0].plain, "under");
+        assert_eq!(flagged.legalese[7].phrase, "thereof");
+        assert_eq!(flagged.legalese[7].line, 11);
+        assert!(!report.within_thresholds);
+        assert_eq!(report.legalese_summary.len(), 8);
+        assert_eq!(report.legalese_summary[0].phrase, "aforementioned");
+
+        let lenient = PlainLanguageThresholds {
+            max_legalese_per_100_words: 50.0,
+            ..PlainLanguageThresholds::default()
+        };
+        let report = score_document(letter, &lenient);
+        assert_eq!(report.flagged.len(), 1);
+        assert_eq!(report.flagged[0].reasons.len(), 1);
+        assert!(report.flagged[0].reasons[0].starts_with("grade level"));
+    }
+
+    #[test]
+    fn setting_values_are_validated() {
+        let parsed = parse_setting_value(&serde_json::json!({"max_grade": 8})).unwrap();
+        assert_eq!(parsed.max_grade, 8.0);
+        assert_eq!(
+            parsed.max_legalese_per_100_words,
+            DEFAULT_MAX_LEGALESE_PER_100_WORDS
+        );
+        assert!(parse_setting_value(&serde_json::json!({"max_grade": 0})).is_err());
+        assert!(parse_setting_value(&serde_json::json!({"max_grade": "high"})).is_err());
+    }
+}
@@ -0,0 +1,489 @@
+//! Matter scaffolds: the workspace files written when a matter is created.
+//!
+//! Every matter gets the base scaffold (metadata, README, intake/filing
+//! checklists, logs, and drafting templates). A practice-area scaffold from
+//! [`PRACTICE_AREA_SCAFFOLDS`] is layered on top when the matter's
+//! `practice_area` names one of its aliases, so family, personal injury,
+//! corporate, and immigration matters start with the checklists and trackers
+//! they actually use.
+//!
+//! Firms customize the scaffold without recompiling by adding files under
+//! `<matter_root>/_template/`. Each file is rendered with the docgen template
+//! engine against the new matter's intake data and written at the same
+//! relative path, replacing the built-in file there; a template that renders
+//! blank drops that path from the scaffold instead. `matter.yaml` is always
+//! generated from the intake request, so the example copy seeded in
+//! `_template/` is never instantiated.
+
+use crate::error::WorkspaceError;
+use crate::workspace::Workspace;
+
+/// One file in a practice-area scaffold, relative to the matter root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScaffoldFile {
+    pub path: &'static str,
+    pub content: &'static str,
+}
+
+/// A practice-area scaffold in the registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PracticeAreaScaffold {
+    pub id: &'static str,
+    pub label: &'static str,
+    /// Phrases matched as whole words against the matter's practice area.
+    pub aliases: &'static [&'static str],
+    pub files: &'static [ScaffoldFile],
+}
+
+/// Values substituted into the base scaffold.
+#[derive(Debug, Clone, Copy)]
+pub struct ScaffoldContext<'a> {
+    pub matter_id: &'a str,
+    pub client: &'a str,
+    /// Serialized `matter.yaml` body.
+    pub matter_yaml: &'a str,
+}
+
+pub const PRACTICE_AREA_SCAFFOLDS: &[PracticeAreaScaffold] = &[
+    PracticeAreaScaffold {
+        id: "family",
+        label: "Family law",
+        aliases: &[
+            "family",
+            "divorce",
+            "custody",
+            "matrimonial",
+            "separation",
+            "child support",
+        ],
+        files: &[
+            ScaffoldFile {
+                path: "workflows/family_intake_checklist.md",
+                content: "# Family Law Intake Checklist\n\n- [ ] Date of marriage/cohabitation and date of separation\n- [ ] Children: names, ages, current parenting arrangements\n- [ ] Existing orders, agreements, or pending proceedings\n- [ ] Safety screening (family violence, urgent relief needed)\n- [ ] Financial disclosure requested from both parties\n- [ ] Limitation periods for property and support claims calendared\n",
+            },
+            ScaffoldFile {
+                path: "financial/disclosure_tracker.md",
+                content: "# Financial Disclosure Tracker\n\n| Document | Party | Requested | Received | Notes |\n|---|---|---|---|---|\n| Income tax returns (3 years) | | | | |\n| Notices of assessment (3 years) | | | | |\n| Pay stubs / income statements | | | | |\n| Financial statement (sworn) | | | | |\n| Property valuations | | | | |\n",
+            },
+            ScaffoldFile {
+                path: "templates/parenting_plan.md",
+                content: "# Parenting Plan\n\n## Decision-Making Responsibility\n\n## Parenting Time Schedule\n\n## Holidays and Special Occasions\n\n## Communication Between Parents\n\n## Relocation and Travel\n\n## Dispute Resolution\n",
+            },
+            ScaffoldFile {
+                path: "templates/property_equalization.md",
+                content: "# Property and Equalization Worksheet\n\n| Asset / Debt | Owner | Value at Marriage | Value at Separation | Excluded? | Source |\n|---|---|---|---|---|---|\n",
+            },
+        ],
+    },
+    PracticeAreaScaffold {
+        id: "personal_injury",
+        label: "Personal injury",
+        aliases: &[
+            "personal injury",
+            "pi",
+            "motor vehicle",
+            "mva",
+            "slip and fall",
+            "medical malpractice",
+            "wrongful death",
+        ],
+        files: &[
+            ScaffoldFile {
+                path: "workflows/pi_intake_checklist.md",
+                content: "# Personal Injury Intake Checklist\n\n- [ ] Date, place, and mechanism of injury\n- [ ] Limitation period and notice deadlines calendared\n- [ ] Treating providers and medical record authorizations signed\n- [ ] Insurance coverage identified (own policy, defendant, accident benefits)\n- [ ] Photos, police/incident reports, and witness details collected\n- [ ] Pre-accident employment and income documented\n",
+            },
+            ScaffoldFile {
+                path: "medical/records_tracker.md",
+                content: "# Medical Records Tracker\n\n| Provider | Dates of Service | Requested | Received | Summary | Source |\n|---|---|---|---|---|---|\n",
+            },
+            ScaffoldFile {
+                path: "damages/special_damages.md",
+                content: "# Special Damages\n\n| Category | Date | Amount | Payor | Receipt / Source |\n|---|---|---|---|---|\n| Medical / rehab | | | | |\n| Lost income | | | | |\n| Out-of-pocket | | | | |\n",
+            },
+            ScaffoldFile {
+                path: "insurance/claims_log.md",
+                content: "# Insurance Claims Log\n\n| Insurer | Claim # | Adjuster | Coverage | Status | Notes |\n|---|---|---|---|---|---|\n",
+            },
+            ScaffoldFile {
+                path: "templates/demand_letter.md",
+                content: "# Demand Letter Template\n\n## Liability\n\n## Injuries and Treatment (Cited)\n\n## Special Damages\n\n## General Damages\n\n## Demand and Deadline\n",
+            },
+        ],
+    },
+    PracticeAreaScaffold {
+        id: "corporate",
+        label: "Corporate",
+        aliases: &[
+            "corporate",
+            "m a",
+            "mergers",
+            "acquisitions",
+            "securities",
+            "financing",
+            "business law",
+        ],
+        files: &[
+            ScaffoldFile {
+                path: "workflows/closing_checklist.md",
+                content: "# Transaction Closing Checklist\n\n| Deliverable | Responsible Party | Status | Signed | Notes |\n|---|---|---|---|---|\n| Definitive agreement | | | | |\n| Board / shareholder approvals | | | | |\n| Third-party consents | | | | |\n| Regulatory filings | | | | |\n| Closing certificates | | | | |\n",
+            },
+            ScaffoldFile {
+                path: "diligence/due_diligence_tracker.md",
+                content: "# Due Diligence Tracker\n\n| Area | Request | Data Room Ref | Reviewer | Issue / Risk | Status |\n|---|---|---|---|---|---|\n",
+            },
+            ScaffoldFile {
+                path: "corporate/entity_records.md",
+                content: "# Entity Records\n\n| Entity | Jurisdiction | Registration # | Directors / Officers | Share Capital | Minute Book Reviewed |\n|---|---|---|---|---|---|\n",
+            },
+            ScaffoldFile {
+                path: "templates/board_resolution.md",
+                content: "# Board Resolution Template\n\n## Recitals\n\n## Resolutions\n\n## Authorization of Officers\n\n## Effective Date and Signatures\n",
+            },
+            ScaffoldFile {
+                path: "templates/disclosure_schedule.md",
+                content: "# Disclosure Schedule\n\n| Section | Representation | Exception / Disclosure | Source |\n|---|---|---|---|\n",
+            },
+        ],
+    },
+    PracticeAreaScaffold {
+        id: "immigration",
+        label: "Immigration",
+        aliases: &[
+            "immigration",
+            "naturalization",
+            "green card",
+            "adjustment of status",
+            "uscis",
+        ],
+        files: &[
+            ScaffoldFile {
+                path: "workflows/immigration_intake_checklist.md",
+                content: "# Immigration Intake Checklist\n\n- [ ] Immigration history: entries, status, prior petitions and A-Number\n- [ ] Identity documents: passport, birth certificate, marriage certificate\n- [ ] Criminal and immigration-violation screening\n- [ ] Case data entered in immigration/case_data.yaml\n- [ ] Forms checked for completeness before signature\n- [ ] Receipt, biometrics, and RFE deadlines calendared from USCIS notices\n",
+            },
+            ScaffoldFile {
+                path: "immigration/case_data.yaml",
+                content: "# Immigration case data. Form fields read these keys; dates are\n# YYYY-MM-DD. Blank values are reported as missing.\npetition:\n  relationship:\n  marriage_date:\n  receipt_number:\npetitioner:\n  status:\n  family_name:\n  given_name:\n  middle_name:\n  date_of_birth:\n  country_of_birth:\n  a_number:\n  ssn:\n  mailing_address:\n  phone:\n  email:\nbeneficiary:\n  family_name:\n  given_name:\n  middle_name:\n  date_of_birth:\n  country_of_birth:\n  a_number:\n  address:\napplicant:\n  family_name:\n  given_name:\n  middle_name:\n  date_of_birth:\n  country_of_birth:\n  country_of_citizenship:\n  a_number:\n  ssn:\n  last_arrival_date:\n  permanent_resident_date:\n  mailing_address:\n  phone:\n  email:\nead:\n  eligibility_category:\nnaturalization:\n  eligibility_basis:\n",
+            },
+            ScaffoldFile {
+                path: "immigration/uscis_notices_log.md",
+                content: "# USCIS Notices Log\n\n| Form | Receipt # | Notice | Notice Date | Action Due | Docketed |\n|---|---|---|---|---|---|\n",
+            },
+        ],
+    },
+];
+
+/// Directory under the matter root holding the firm's scaffold templates.
+pub const TEMPLATE_DIR: &str = "_template";
+
+/// Cap on files instantiated from `_template/`.
+pub const MAX_TEMPLATE_FILES: usize = 200;
+
+fn normalize_words(value: &str) -> String {
+    value
+        .to_ascii_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// True when any alias appears in `practice_area` as whole words, ignoring
+/// case and punctuation ("Commercial M&A" contains the alias "m a").
+pub fn practice_area_matches<S: AsRef<str>>(practice_area: &str, aliases: &[S]) -> bool {
+    let area = format!(" {} ", normalize_words(practice_area));
+    aliases.iter().any(|alias| {
+        let alias = normalize_words(alias.as_ref());
+        !alias.is_empty() && area.contains(&format!(" {alias} "))
+    })
+}
+
+/// Pick the registry scaffold for a matter's practice area, if any alias
+/// appears in it as whole words (e.g. "Commercial M&A" selects corporate).
+pub fn scaffold_for_practice_area(
+    practice_area: Option<&str>,
+) -> Option<&'static PracticeAreaScaffold> {
+    let area = practice_area?;
+    PRACTICE_AREA_SCAFFOLDS
+        .iter()
+        .find(|scaffold| practice_area_matches(area, scaffold.aliases))
+}
+
+/// Build the files for a new matter as `(relative path, content)` pairs:
+/// the base scaffold followed by the practice-area files, if any.
+pub fn matter_scaffold_files(
+    ctx: &ScaffoldContext<'_>,
+    practice: Option<&PracticeAreaScaffold>,
+) -> Vec<(String, String)> {
+    let practice_line = practice
+        .map(|scaffold| format!("Practice area scaffold: {}\n\n", scaffold.label))
+        .unwrap_or_default();
+    let mut files = vec![
+        (
+            "matter.yaml".to_string(),
+            format!(
+                "# Matter metadata schema\n# Required: matter_id, client, confidentiality, retention\n{}",
+                ctx.matter_yaml
+            ),
+        ),
+        (
+            "README.md".to_string(),
+            format!(
+                "# Matter {}\n\nClient: {}\n\n{}This workspace stores privileged legal work product.\n\n## Suggested Workflow\n\n1. Intake and conflicts\n2. Facts and chronology\n3. Research and authority synthesis\n4. Drafting and review\n5. Filing and follow-up\n",
+                ctx.matter_id, ctx.client, practice_line
+            ),
+        ),
+        (
+            "workflows/intake_checklist.md".to_string(),
+            "# Intake Checklist\n\n- [ ] Confirm engagement and scope\n- [ ] Confirm client contact and billing details\n- [ ] Run conflict check and document result\n- [ ] Capture key deadlines and court dates\n- [ ] Identify required initial filings or responses\n".to_string(),
+        ),
+        (
+            "workflows/review_and_filing_checklist.md".to_string(),
+            "# Review and Filing Checklist\n\n- [ ] Separate facts from analysis in final draft\n- [ ] Verify citation format coverage for factual/legal assertions\n- [ ] Confirm privilege/confidentiality review complete\n- [ ] Final QA pass and attorney approval recorded\n- [ ] Filing/service steps completed and logged\n".to_string(),
+        ),
+        (
+            "deadlines/calendar.md".to_string(),
+            "# Deadlines and Hearings\n\n| Date | Deadline / Event | Owner | Status | Source |\n|---|---|---|---|---|\n".to_string(),
+        ),
+        (
+            "facts/key_facts.md".to_string(),
+            "# Key Facts Log\n\n| Fact | Source | Confidence | Notes |\n|---|---|---|---|\n".to_string(),
+        ),
+        (
+            "research/authority_table.md".to_string(),
+            "# Authority Table\n\n| Authority | Holding / Principle | Relevance | Risk / Limit | Citation |\n|---|---|---|---|---|\n".to_string(),
+        ),
+        (
+            "discovery/request_tracker.md".to_string(),
+            "# Discovery Request Tracker\n\n| Request / Topic | Served / Received | Response Due | Status | Notes |\n|---|---|---|---|---|\n".to_string(),
+        ),
+        (
+            "communications/contact_log.md".to_string(),
+            crate::legal::correspondence::CONTACT_LOG_TEMPLATE.to_string(),
+        ),
+        (
+            "templates/research_memo.md".to_string(),
+            "# Research Memo Template\n\n## Question Presented\n\n## Brief Answer\n\n## Facts (Cited)\n\n## Analysis\n\n## Authorities\n\n## Open Questions\n".to_string(),
+        ),
+        (
+            "templates/chronology.md".to_string(),
+            "# Chronology\n\n| Date | Event | Source |\n|---|---|---|\n".to_string(),
+        ),
+        (
+            "templates/legal_memo.md".to_string(),
+            "# Legal Memo Template\n\n## Issue\n\n## Brief Answer\n\n## Facts (Cited)\n\n## Analysis\n\n## Conclusion\n\n## Risk / Uncertainty\n".to_string(),
+        ),
+        (
+            "templates/contract_issues.md".to_string(),
+            "# Contract Issue List\n\n| Clause / Topic | Risk | Recommendation | Source |\n|---|---|---|---|\n".to_string(),
+        ),
+        (
+            "templates/discovery_plan.md".to_string(),
+            "# Discovery Plan\n\n## Custodians\n\n## Data Sources\n\n## Requests\n\n## Objections / Risks\n\n## Source Traceability\n".to_string(),
+        ),
+        (
+            "templates/research_synthesis.md".to_string(),
+            "# Research Synthesis\n\n## Question Presented\n\n## Authorities Reviewed\n\n## Facts (Cited)\n\n## Analysis\n\n## Risk / Uncertainty\n".to_string(),
+        ),
+        (
+            format!(
+                "templates/{}",
+                crate::legal::status_report::TEMPLATE_NAME
+            ),
+            crate::legal::status_report::TEMPLATE_BODY.to_string(),
+        ),
+        (
+            format!(
+                "templates/{}",
+                crate::legal::closeout::CLOSING_LETTER_TEMPLATE_NAME
+            ),
+            crate::legal::closeout::CLOSING_LETTER_TEMPLATE_BODY.to_string(),
+        ),
+    ];
+    if let Some(practice) = practice {
+        files.extend(
+            practice
+                .files
+                .iter()
+                .map(|file| (file.path.to_string(), file.content.to_string())),
+        );
+    }
+    files
+}
+
+/// Render context for `_template/` files: `matter` (the `matter.yaml`
+/// fields), `client.name`, and the selected practice-area `scaffold`.
+pub fn template_context(
+    metadata: &crate::legal::matter::MatterMetadata,
+    practice: Option<&PracticeAreaScaffold>,
+) -> serde_json::Value {
+    serde_json::json!({
+        "matter": metadata,
+        "client": { "name": metadata.client },
+        "scaffold": practice.map(|scaffold| serde_json::json!({
+            "id": scaffold.id,
+            "label": scaffold.label,
+        })),
+    })
+}
+
+/// Render `templates` (relative path, body) and merge them into `files`.
+pub fn apply_templates(
+    files: &mut Vec<(String, String)>,
+    templates: &[(String, String)],
+    context: &serde_json::Value,
+) -> Result<(), String> {
+    for (path, body) in templates {
+        let rendered = crate::legal::docgen::render_template(body, context)
+            .map_err(|err| format!("scaffold template '{path}' failed to render: {err}"))?;
+        files.retain(|(existing, _)| existing != path);
+        if !rendered.trim().is_empty() {
+            files.push((path.clone(), rendered));
+        }
+    }
+    Ok(())
+}
+
+/// Read the firm's `_template/` files as (relative path, body) pairs,
+/// skipping `matter.yaml`. An absent directory yields no templates.
+pub async fn load_workspace_templates(
+    workspace: &Workspace,
+    matter_root: &str,
+) -> Result<Vec<(String, String)>, String> {
+    let template_root = format!("{}/{TEMPLATE_DIR}", matter_root.trim_matches('/'));
+    let mut pending = vec![template_root.clone()];
+    let mut templates = Vec::new();
+    while let Some(dir) = pending.pop() {
+        let entries = match workspace.list(&dir).await {
+            Ok(entries) => entries,
+            Err(WorkspaceError::DocumentNotFound { .. }) => Vec::new(),
+            Err(err) => return Err(format!("failed to list '{dir}': {err}")),
+        };
+        for entry in entries {
+            if entry.is_directory {
+                pending.push(entry.path);
+                continue;
+            }
+            let Some(relative) = entry.path.strip_prefix(&format!("{template_root}/")) else {
+                continue;
+            };
+            if relative == "matter.yaml" {
+                continue;
+            }
+            if templates.len() >= MAX_TEMPLATE_FILES {
+                return Err(format!(
+                    "'{template_root}' has more than {MAX_TEMPLATE_FILES} files"
+                ));
+            }
+            let doc = workspace
+                .read(&entry.path)
+                .await
+                .map_err(|err| format!("failed to read '{}': {err}", entry.path))?;
+            templates.push((relative.to_string(), doc.content));
+        }
+    }
+    templates.sort();
+    Ok(templates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn practice_area_selects_scaffold_by_whole_word_alias() {
+        let id = |area: &str| scaffold_for_practice_area(Some(area)).map(|s| s.id);
+        assert_eq!(id("Family Law"), Some("family"));
+        assert_eq!(id("Divorce / custody"), Some("family"));
+        assert_eq!(id("PI - motor vehicle"), Some("personal_injury"));
+        assert_eq!(id("Commercial M&A"), Some("corporate"));
+        assert_eq!(id("Naturalization / green card"), Some("immigration"));
+        assert_eq!(id("Pipeline regulatory"), None);
+        assert_eq!(id("commercial litigation"), None);
+        assert_eq!(scaffold_for_practice_area(None), None);
+    }
+
+    #[test]
+    fn practice_scaffold_files_extend_the_base_without_collisions() {
+        let ctx = ScaffoldContext {
+            matter_id: "smith-divorce",
+            client: "Jane Smith",
+            matter_yaml: "matter_id: smith-divorce\n",
+        };
+        let base = matter_scaffold_files(&ctx, None);
+        for scaffold in PRACTICE_AREA_SCAFFOLDS {
+            let files = matter_scaffold_files(&ctx, Some(scaffold));
+            assert_eq!(files.len(), base.len() + scaffold.files.len());
+            let mut paths: Vec<_> = files.iter().map(|(path, _)| path.as_str()).collect();
+            paths.sort_unstable();
+            paths.dedup();
+            assert_eq!(paths.len(), files.len(), "{} collides", scaffold.id);
+            assert!(files[1].1.contains(scaffold.label));
+        }
+        assert!(base[0].1.contains("matter_id: smith-divorce"));
+        assert!(!base[1].1.contains("Practice area scaffold"));
+    }
+
+    #[test]
+    fn templates_render_replace_and_drop_scaffold_files() {
+        let metadata = crate::legal::matter::MatterMetadata {
+            matter_id: "acme-v-foo".to_string(),
+            client: "Acme Corp".to_string(),
+            team: vec!["Lead Counsel".to_string()],
+            confidentiality: "attorney-client-privileged".to_string(),
+            adversaries: vec!["Foo LLC".to_string()],
+            retention: "follow-firm-policy".to_string(),
+            jurisdiction: None,
+            practice_area: Some("corporate".to_string()),
+            opened_date: None,
+        };
+        let practice = scaffold_for_practice_area(metadata.practice_area.as_deref());
+        let context = template_context(&metadata, practice);
+        let mut files = vec![
+            (
+                "workflows/intake_checklist.md".to_string(),
+                "old".to_string(),
+            ),
+            (
+                "discovery/request_tracker.md".to_string(),
+                "tracker".to_string(),
+            ),
+        ];
+        apply_templates(
+            &mut files,
+            &[
+                (
+                    "workflows/intake_checklist.md".to_string(),
+                    "# Intake for {{ client.name }}\n- [ ] Screen {{ matter.adversaries | join(sep=\", \") }}\n".to_string(),
+                ),
+                (
+                    "discovery/request_tracker.md".to_string(),
+                    "{% if scaffold.id != \"corporate\" %}tracker{% endif %}".to_string(),
+                ),
+                ("firm/engagement.md".to_string(), "Matter {{ matter.matter_id }}".to_string()),
+            ],
+            &context,
+        )
+        .expect("templates render");
+        assert_eq!(
+            files,
+            vec![
+                (
+                    "workflows/intake_checklist.md".to_string(),
+                    "# Intake for Acme Corp\n- [ ] Screen Foo LLC\n".to_string()
+                ),
+                (
+                    "firm/engagement.md".to_string(),
+                    "Matter acme-v-foo".to_string()
+                ),
+            ]
+        );
+
+        let err = apply_templates(
+            &mut files,
+            &[("broken.md".to_string(), "{% if %}".to_string())],
+            &context,
+        )
+        .expect_err("syntax errors surface");
+        assert!(err.contains("broken.md"));
+    }
+}
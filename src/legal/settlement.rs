@@ -0,0 +1,326 @@
+//! Settlement negotiations: client authority checks, the current gap
+//! between the parties, and the negotiation-history exhibit.
+//!
+//! Authority is privileged. It is checked against the client's own
+//! proposals but never appears in the negotiation history, which is meant
+//! to be shown to a mediator or attached to a brief.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::db::{
+    SettlementAuthorityKind, SettlementAuthorityRecord, SettlementProposalKind,
+    SettlementProposalRecord, SettlementProposalStatus, SettlementSide,
+};
+use crate::legal::damages::format_money;
+use crate::legal::markdown::table_cell;
+
+pub fn negotiation_history_path(matter_prefix: &str) -> String {
+    format!("{matter_prefix}/settlement/negotiation_history.md")
+}
+
+/// Result of checking a proposed amount against the client's authority.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuthorityCheck {
+    pub authority_id: String,
+    pub kind: SettlementAuthorityKind,
+    pub limit: Decimal,
+    pub amount: Decimal,
+    pub within_authority: bool,
+    /// How far past the limit the amount goes; zero when within authority.
+    pub excess: Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+/// Check `amount` against a grant. A `Minimum` grant is exceeded by
+/// accepting or demanding less; a `Maximum` grant by offering more.
+pub fn check_authority(authority: &SettlementAuthorityRecord, amount: Decimal) -> AuthorityCheck {
+    let excess = match authority.kind {
+        SettlementAuthorityKind::Minimum => authority.amount - amount,
+        SettlementAuthorityKind::Maximum => amount - authority.amount,
+    }
+    .max(Decimal::ZERO);
+    let within_authority = excess.is_zero();
+    let warning = (!within_authority).then(|| match authority.kind {
+        SettlementAuthorityKind::Minimum => format!(
+            "{} is {} below the client's minimum authority of {} (granted {})",
+            format_money(amount),
+            format_money(excess),
+            format_money(authority.amount),
+            authority.granted_on
+        ),
+        SettlementAuthorityKind::Maximum => format!(
+            "{} is {} above the client's maximum authority of {} (granted {})",
+            format_money(amount),
+            format_money(excess),
+            format_money(authority.amount),
+            authority.granted_on
+        ),
+    });
+    AuthorityCheck {
+        authority_id: authority.id.to_string(),
+        kind: authority.kind,
+        limit: authority.amount,
+        amount,
+        within_authority,
+        excess,
+        warning,
+    }
+}
+
+/// Where the negotiation stands: the latest monetary demand and offer.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct NegotiationPosition {
+    pub latest_demand: Option<Decimal>,
+    pub latest_offer: Option<Decimal>,
+    /// Demand minus offer, when both are on the table.
+    pub gap: Option<Decimal>,
+}
+
+/// Position from proposals in negotiation order. Withdrawn proposals are
+/// off the table and ignored.
+pub fn negotiation_position(proposals: &[SettlementProposalRecord]) -> NegotiationPosition {
+    let latest = |kind: SettlementProposalKind| {
+        proposals
+            .iter()
+            .rev()
+            .filter(|p| p.kind == kind && p.status != SettlementProposalStatus::Withdrawn)
+            .find_map(|p| p.amount)
+    };
+    let latest_demand = latest(SettlementProposalKind::Demand);
+    let latest_offer = latest(SettlementProposalKind::Offer);
+    NegotiationPosition {
+        latest_demand,
+        latest_offer,
+        gap: latest_demand
+            .zip(latest_offer)
+            .map(|(demand, offer)| demand - offer),
+    }
+}
+
+fn kind_label(proposal: &SettlementProposalRecord) -> &'static str {
+    match proposal.kind {
+        SettlementProposalKind::Demand => "Demand",
+        SettlementProposalKind::Offer => "Offer",
+    }
+}
+
+/// Negotiation-history exhibit: every demand and offer in order with the
+/// movement from the same party's previous figure. `caption` is a rendered
+/// case caption placed above the table.
+pub fn render_negotiation_history(
+    matter_label: &str,
+    caption: Option<&str>,
+    proposals: &[SettlementProposalRecord],
+    generated_at: DateTime<Utc>,
+) -> String {
+    let mut out = String::new();
+    if let Some(caption) = caption {
+        out.push_str(caption.trim_end());
+        out.push_str("\n\n");
+    }
+    out.push_str(&format!(
+        "# Settlement Negotiation History: {matter_label}\n\nPrepared: {}\n\n",
+        generated_at.format("%Y-%m-%d")
+    ));
+    if proposals.is_empty() {
+        out.push_str("- No settlement proposals recorded.\n");
+        return out;
+    }
+
+    out.push_str(
+        "| No. | Date | From | To | Type | Amount | Movement | Terms | Status |\n|---|---|---|---|---|---|---|---|---|\n",
+    );
+    for (index, proposal) in proposals.iter().enumerate() {
+        let previous = proposals[..index]
+            .iter()
+            .rev()
+            .filter(|p| p.kind == proposal.kind && p.side == proposal.side)
+            .find_map(|p| p.amount);
+        let movement = match (proposal.amount, previous) {
+            (Some(amount), Some(previous)) if amount > previous => {
+                format!("+{}", format_money(amount - previous))
+            }
+            (Some(amount), Some(previous)) if amount < previous => format_money(amount - previous),
+            (Some(_), Some(_)) => "no change".to_string(),
+            (Some(_), None) => "opening".to_string(),
+            (None, _) => String::new(),
+        };
+        let mut terms = proposal.terms.clone().unwrap_or_default();
+        if let Some(expires_on) = proposal.expires_on {
+            if !terms.is_empty() {
+                terms.push_str("; ");
+            }
+            terms.push_str(&format!("open until {expires_on}"));
+        }
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            index + 1,
+            proposal.proposed_on,
+            table_cell(&proposal.from_party),
+            table_cell(proposal.to_party.as_deref().unwrap_or_default()),
+            kind_label(proposal),
+            proposal.amount.map(format_money).unwrap_or_default(),
+            movement,
+            table_cell(&terms),
+            proposal.status.as_str(),
+        ));
+    }
+
+    let position = negotiation_position(proposals);
+    out.push_str("\n## Current Positions\n\n");
+    for (label, amount) in [
+        ("Last demand", position.latest_demand),
+        ("Last offer", position.latest_offer),
+        ("Gap", position.gap),
+    ] {
+        out.push_str(&format!(
+            "- {label}: {}\n",
+            amount
+                .map(format_money)
+                .unwrap_or_else(|| "none".to_string())
+        ));
+    }
+    let client_moves = proposals
+        .iter()
+        .filter(|p| p.side == SettlementSide::Client)
+        .count();
+    out.push_str(&format!(
+        "- Exchanges: {} ({client_moves} by the client, {} by the opposing side)\n",
+        proposals.len(),
+        proposals.len() - client_moves
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn proposal(
+        kind: SettlementProposalKind,
+        side: SettlementSide,
+        from: &str,
+        amount: Option<i64>,
+        on: &str,
+    ) -> SettlementProposalRecord {
+        SettlementProposalRecord {
+            id: Uuid::new_v4(),
+            user_id: "default".to_string(),
+            matter_id: "demo".to_string(),
+            kind,
+            side,
+            from_party: from.to_string(),
+            to_party: None,
+            amount: amount.map(Decimal::from),
+            terms: None,
+            proposed_on: NaiveDate::parse_from_str(on, "%Y-%m-%d").unwrap(),
+            expires_on: None,
+            status: SettlementProposalStatus::Open,
+            response_notes: None,
+            created_by: "default".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn authority(kind: SettlementAuthorityKind, amount: i64) -> SettlementAuthorityRecord {
+        SettlementAuthorityRecord {
+            id: Uuid::new_v4(),
+            user_id: "default".to_string(),
+            matter_id: "demo".to_string(),
+            kind,
+            amount: Decimal::from(amount),
+            granted_on: NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            granted_by: None,
+            notes: None,
+            created_by: "default".to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn authority_checks_respect_direction() {
+        let floor = authority(SettlementAuthorityKind::Minimum, 150_000);
+        assert!(check_authority(&floor, Decimal::from(175_000)).within_authority);
+        let below = check_authority(&floor, Decimal::from(140_000));
+        assert!(!below.within_authority);
+        assert_eq!(below.excess, Decimal::from(10_000));
+        assert_eq!(
+            below.warning.as_deref(),
+            Some(
+                "$140,000.00 is $10,000.00 below the client's minimum authority of $150,000.00 (granted 2025-03-01)"
+            )
+        );
+
+        let ceiling = authority(SettlementAuthorityKind::Maximum, 80_000);
+        assert!(check_authority(&ceiling, Decimal::from(80_000)).within_authority);
+        assert_eq!(
+            check_authority(&ceiling, Decimal::from(95_000)).excess,
+            Decimal::from(15_000)
+        );
+    }
+
+    #[test]
+    fn history_tracks_movement_and_gap() {
+        let mut withdrawn = proposal(
+            SettlementProposalKind::Offer,
+            SettlementSide::Opposing,
+            "Acme Insurance",
+            Some(90_000),
+            "2025-04-01",
+        );
+        withdrawn.status = SettlementProposalStatus::Withdrawn;
+        let proposals = vec![
+            proposal(
+                SettlementProposalKind::Demand,
+                SettlementSide::Client,
+                "Jane Roe",
+                Some(250_000),
+                "2025-01-10",
+            ),
+            proposal(
+                SettlementProposalKind::Offer,
+                SettlementSide::Opposing,
+                "Acme Insurance",
+                Some(40_000),
+                "2025-02-01",
+            ),
+            proposal(
+                SettlementProposalKind::Demand,
+                SettlementSide::Client,
+                "Jane Roe",
+                Some(200_000),
+                "2025-03-05",
+            ),
+            proposal(
+                SettlementProposalKind::Offer,
+                SettlementSide::Opposing,
+                "Acme Insurance",
+                Some(65_000),
+                "2025-03-20",
+            ),
+            withdrawn,
+        ];
+
+        let position = negotiation_position(&proposals);
+        assert_eq!(position.latest_demand, Some(Decimal::from(200_000)));
+        assert_eq!(position.latest_offer, Some(Decimal::from(65_000)));
+        assert_eq!(position.gap, Some(Decimal::from(135_000)));
+
+        let history = render_negotiation_history("Roe v. Acme", None, &proposals, Utc::now());
+        assert!(history.contains(
+            "| 1 | 2025-01-10 | Jane Roe |  | Demand | $250,000.00 | opening |  | open |"
+        ));
+        assert!(history.contains("| Demand | $200,000.00 | -$50,000.00 |"));
+        assert!(history.contains("| Offer | $65,000.00 | +$25,000.00 |"));
+        assert!(history.contains("| 5 | 2025-04-01 | Acme Insurance |  | Offer | $90,000.00 | +$25,000.00 |  | withdrawn |"));
+        assert!(history.contains("- Gap: $135,000.00"));
+        assert!(history.contains("- Exchanges: 5 (2 by the client, 3 by the opposing side)"));
+    }
+}
@@ -0,0 +1,361 @@
+//! Matter stage workflows: per-practice-area stages, allowed transitions,
+//! entry prerequisites, and tasks created on entry.
+//!
+//! Defaults ship in `stage_workflows.toml`; a firm replaces them with the
+//! `matter_stage_workflows` setting (same structure, as JSON).
+
+use std::collections::HashSet;
+use std::sync::{Arc, LazyLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::Database;
+
+/// Setting key holding a firm's replacement workflow list.
+pub const STAGE_WORKFLOWS_SETTING_KEY: &str = "matter_stage_workflows";
+
+/// Workflow used when no other workflow matches a matter's practice area.
+pub const FALLBACK_WORKFLOW_ID: &str = "general";
+
+const MAX_STAGE_ID_LEN: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageWorkflow {
+    pub id: String,
+    pub label: String,
+    #[serde(default)]
+    pub practice_areas: Vec<String>,
+    pub stages: Vec<StageDefinition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageDefinition {
+    pub id: String,
+    pub label: String,
+    #[serde(default)]
+    pub next: Vec<String>,
+    #[serde(default)]
+    pub required_artifacts: Vec<String>,
+    #[serde(default)]
+    pub complete_checklists: Vec<String>,
+    #[serde(default)]
+    pub tasks: Vec<String>,
+}
+
+impl StageWorkflow {
+    pub fn stage(&self, id: &str) -> Option<&StageDefinition> {
+        self.stages.iter().find(|stage| stage.id == id)
+    }
+
+    /// Stages reachable from `current`. A matter with no stage may enter the
+    /// first stage or anything the first stage leads to; a stage that is not
+    /// part of this workflow (set before workflows existed, or by a since
+    /// edited configuration) may move to any stage.
+    pub fn allowed_next(&self, current: Option<&str>) -> Vec<&StageDefinition> {
+        let ids: Vec<&str> = match current.map(|id| (id, self.stage(id))) {
+            Some((_, Some(stage))) => stage.next.iter().map(String::as_str).collect(),
+            Some((_, None)) => self.stages.iter().map(|stage| stage.id.as_str()).collect(),
+            None => match self.stages.first() {
+                Some(first) => std::iter::once(first.id.as_str())
+                    .chain(first.next.iter().map(String::as_str))
+                    .collect(),
+                None => Vec::new(),
+            },
+        };
+        self.stages
+            .iter()
+            .filter(|stage| ids.contains(&stage.id.as_str()))
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct StageWorkflowConfig {
+    workflows: Vec<StageWorkflow>,
+}
+
+static DEFAULT_WORKFLOWS: LazyLock<Result<Vec<StageWorkflow>, String>> =
+    LazyLock::new(|| parse_default_workflows(include_str!("stage_workflows.toml")));
+
+fn parse_default_workflows(raw: &str) -> Result<Vec<StageWorkflow>, String> {
+    let parsed: StageWorkflowConfig =
+        toml::from_str(raw).map_err(|e| format!("invalid stage workflows TOML: {}", e))?;
+    validate_workflows(&parsed.workflows)?;
+    Ok(parsed.workflows)
+}
+
+pub fn default_workflows() -> Result<&'static [StageWorkflow], String> {
+    match &*DEFAULT_WORKFLOWS {
+        Ok(workflows) => Ok(workflows.as_slice()),
+        Err(err) => Err(err.clone()),
+    }
+}
+
+/// Check that ids are unique and non-empty, every `next` names a stage in the
+/// same workflow, and prerequisite paths stay inside the matter.
+pub fn validate_workflows(workflows: &[StageWorkflow]) -> Result<(), String> {
+    if workflows.is_empty() {
+        return Err("at least one workflow is required".to_string());
+    }
+    let mut workflow_ids = HashSet::new();
+    for workflow in workflows {
+        validate_id("workflow id", &workflow.id)?;
+        if !workflow_ids.insert(workflow.id.as_str()) {
+            return Err(format!("duplicate workflow id '{}'", workflow.id));
+        }
+        if workflow.stages.is_empty() {
+            return Err(format!("workflow '{}' has no stages", workflow.id));
+        }
+        let mut stage_ids = HashSet::new();
+        for stage in &workflow.stages {
+            validate_id("stage id", &stage.id)?;
+            if !stage_ids.insert(stage.id.as_str()) {
+                return Err(format!(
+                    "duplicate stage '{}' in workflow '{}'",
+                    stage.id, workflow.id
+                ));
+            }
+        }
+        for stage in &workflow.stages {
+            if let Some(missing) = stage
+                .next
+                .iter()
+                .find(|next| !stage_ids.contains(next.as_str()))
+            {
+                return Err(format!(
+                    "stage '{}' in workflow '{}' leads to unknown stage '{}'",
+                    stage.id, workflow.id, missing
+                ));
+            }
+            for path in stage
+                .required_artifacts
+                .iter()
+                .chain(stage.complete_checklists.iter())
+            {
+                if path.trim().is_empty()
+                    || path.starts_with('/')
+                    || path.split('/').any(|segment| segment == "..")
+                {
+                    return Err(format!(
+                        "stage '{}' in workflow '{}' has invalid path '{}'",
+                        stage.id, workflow.id, path
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate_id(kind: &str, id: &str) -> Result<(), String> {
+    if id.is_empty()
+        || id.len() > MAX_STAGE_ID_LEN
+        || !id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+    {
+        return Err(format!(
+            "{kind} '{id}' must be 1-{MAX_STAGE_ID_LEN} lowercase letters, digits, '_' or '-'"
+        ));
+    }
+    Ok(())
+}
+
+/// Parse and validate a `matter_stage_workflows` setting value.
+pub fn parse_setting_value(value: &serde_json::Value) -> Result<Vec<StageWorkflow>, String> {
+    let workflows: Vec<StageWorkflow> = serde_json::from_value(value.clone())
+        .map_err(|e| format!("invalid stage workflows: {e}"))?;
+    validate_workflows(&workflows)?;
+    Ok(workflows)
+}
+
+/// The firm's workflows from settings, falling back to the bundled defaults
+/// when the setting is absent or unreadable.
+pub async fn resolve_workflows(
+    store: Option<&Arc<dyn Database>>,
+    user_id: &str,
+) -> Result<Vec<StageWorkflow>, String> {
+    if let Some(store) = store {
+        match store
+            .get_setting(user_id, STAGE_WORKFLOWS_SETTING_KEY)
+            .await
+        {
+            Ok(Some(value)) => match parse_setting_value(&value) {
+                Ok(workflows) => return Ok(workflows),
+                Err(err) => tracing::warn!(
+                    user_id,
+                    "Ignoring invalid {STAGE_WORKFLOWS_SETTING_KEY} setting: {err}"
+                ),
+            },
+            Ok(None) => {}
+            Err(err) => tracing::warn!(
+                user_id,
+                "Failed to read {STAGE_WORKFLOWS_SETTING_KEY} setting; using defaults: {err}"
+            ),
+        }
+    }
+    default_workflows().map(<[StageWorkflow]>::to_vec)
+}
+
+/// The workflow for a practice area: the first whose `practice_areas`
+/// match, else the fallback workflow, else the first workflow.
+pub fn workflow_for_practice_area<'a>(
+    workflows: &'a [StageWorkflow],
+    practice_area: Option<&str>,
+) -> Option<&'a StageWorkflow> {
+    practice_area
+        .and_then(|area| {
+            workflows.iter().find(|workflow| {
+                crate::legal::scaffold::practice_area_matches(area, &workflow.practice_areas)
+            })
+        })
+        .or_else(|| {
+            workflows
+                .iter()
+                .find(|workflow| workflow.id == FALLBACK_WORKFLOW_ID)
+        })
+        .or_else(|| workflows.first())
+}
+
+/// Why a requested stage change is not allowed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransitionError {
+    UnknownStage {
+        workflow: String,
+        stage: String,
+    },
+    NotAllowed {
+        from: Option<String>,
+        to: String,
+        allowed: Vec<String>,
+    },
+}
+
+impl std::fmt::Display for TransitionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownStage { workflow, stage } => {
+                write!(
+                    f,
+                    "Stage '{stage}' is not part of the '{workflow}' workflow"
+                )
+            }
+            Self::NotAllowed { from, to, allowed } => {
+                let allowed = if allowed.is_empty() {
+                    "none".to_string()
+                } else {
+                    allowed.join(", ")
+                };
+                write!(
+                    f,
+                    "Cannot move from '{}' to '{to}' (allowed: {allowed})",
+                    from.as_deref().unwrap_or("no stage")
+                )
+            }
+        }
+    }
+}
+
+/// Validate moving from `current` to `target` within `workflow`.
+pub fn check_transition<'a>(
+    workflow: &'a StageWorkflow,
+    current: Option<&str>,
+    target: &str,
+) -> Result<&'a StageDefinition, TransitionError> {
+    let stage = workflow
+        .stage(target)
+        .ok_or_else(|| TransitionError::UnknownStage {
+            workflow: workflow.id.clone(),
+            stage: target.to_string(),
+        })?;
+    let allowed = workflow.allowed_next(current);
+    if allowed.iter().any(|candidate| candidate.id == target) {
+        Ok(stage)
+    } else {
+        Err(TransitionError::NotAllowed {
+            from: current.map(str::to_string),
+            to: target.to_string(),
+            allowed: allowed.iter().map(|stage| stage.id.clone()).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_workflows_parse_and_validate() {
+        let workflows = default_workflows().expect("bundled workflows");
+        assert!(workflows.iter().any(|w| w.id == FALLBACK_WORKFLOW_ID));
+        assert_eq!(
+            workflow_for_practice_area(workflows, Some("Commercial Litigation")).map(|w| &*w.id),
+            Some("litigation")
+        );
+        assert_eq!(
+            workflow_for_practice_area(workflows, Some("Estates")).map(|w| &*w.id),
+            Some("general")
+        );
+        assert_eq!(
+            workflow_for_practice_area(workflows, None).map(|w| &*w.id),
+            Some("general")
+        );
+    }
+
+    #[test]
+    fn transitions_follow_next_lists() {
+        let workflows = default_workflows().expect("bundled workflows");
+        let litigation = workflows.iter().find(|w| w.id == "litigation").unwrap();
+
+        assert!(check_transition(litigation, None, "intake").is_ok());
+        assert!(check_transition(litigation, None, "pleadings").is_ok());
+        assert!(check_transition(litigation, Some("pleadings"), "discovery").is_ok());
+        assert_eq!(
+            check_transition(litigation, Some("pleadings"), "trial"),
+            Err(TransitionError::NotAllowed {
+                from: Some("pleadings".to_string()),
+                to: "trial".to_string(),
+                allowed: vec!["discovery".to_string(), "settlement".to_string()],
+            })
+        );
+        assert!(matches!(
+            check_transition(litigation, Some("pleadings"), "appeal"),
+            Err(TransitionError::UnknownStage { .. })
+        ));
+        // Free-text stages from before workflows existed can move anywhere.
+        assert!(check_transition(litigation, Some("Prep"), "trial").is_ok());
+    }
+
+    #[test]
+    fn setting_value_is_validated() {
+        let valid = serde_json::json!([{
+            "id": "general",
+            "label": "General",
+            "stages": [
+                {"id": "open", "label": "Open", "next": ["done"], "tasks": ["Kickoff"]},
+                {"id": "done", "label": "Done", "complete_checklists": ["workflows/close.md"]},
+            ],
+        }]);
+        let workflows = parse_setting_value(&valid).expect("valid workflows");
+        assert_eq!(workflows[0].stages[0].tasks, vec!["Kickoff".to_string()]);
+
+        let dangling = serde_json::json!([{
+            "id": "general",
+            "label": "General",
+            "stages": [{"id": "open", "label": "Open", "next": ["missing"]}],
+        }]);
+        assert!(
+            parse_setting_value(&dangling)
+                .unwrap_err()
+                .contains("unknown stage 'missing'")
+        );
+
+        let escaping = serde_json::json!([{
+            "id": "general",
+            "label": "General",
+            "stages": [{"id": "open", "label": "Open", "required_artifacts": ["../secrets.md"]}],
+        }]);
+        assert!(parse_setting_value(&escaping).is_err());
+        assert!(parse_setting_value(&serde_json::json!([])).is_err());
+    }
+}
@@ -0,0 +1,429 @@
+//! Matter status memos compiled by the `matter_status_report` routine action.
+//!
+//! Unlike the weekly client status report (drafted by the agent through the
+//! `client_status_report` tool), a status memo is compiled straight from the
+//! matter's records: open tasks, documents added or updated in the period,
+//! upcoming deadlines, time recorded, and contact-log entries. No LLM is
+//! involved. Memos are written to the matter's `exports/` folder; when the
+//! routine asks for delivery the memo is queued in the client status report
+//! approval flow, so it only reaches the client after an attorney approves
+//! it.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::db::{
+    MatterDeadlineRecord, MatterDeadlineType, MatterDocumentCategory, MatterDocumentRecord,
+    MatterTaskRecord, MatterTaskStatus, TimeEntryRecord,
+};
+use crate::legal::correspondence::ContactLogEntry;
+use crate::legal::status_report::UpcomingDate;
+
+/// Days of upcoming deadlines included in a memo.
+pub const UPCOMING_DAYS: i64 = 30;
+
+/// Upper bound on rows listed per section.
+const MAX_SECTION_ITEMS: usize = 25;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoTask {
+    pub title: String,
+    pub status: String,
+    pub due: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoDocument {
+    pub date: String,
+    pub name: String,
+    pub category: String,
+    /// `added` or `updated` during the period.
+    pub change: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoCommunication {
+    pub date: String,
+    pub with: String,
+    pub channel: String,
+    pub summary: String,
+}
+
+/// Everything a status memo reports for one matter and period.
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusMemo {
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub open_tasks: Vec<MemoTask>,
+    pub recent_documents: Vec<MemoDocument>,
+    pub upcoming_deadlines: Vec<UpcomingDate>,
+    pub hours: Decimal,
+    pub billable_hours: Decimal,
+    pub time_entry_count: usize,
+    pub communications: Vec<MemoCommunication>,
+}
+
+/// `matters/acme/exports/status-report-2026-10-15.md`.
+pub fn memo_path(matter_root: &str, matter_id: &str, date: NaiveDate) -> String {
+    format!(
+        "{}/{matter_id}/exports/status-report-{date}.md",
+        matter_root.trim_matches('/')
+    )
+}
+
+/// Email subject when a memo is queued for delivery.
+pub fn memo_subject(matter_id: &str, date: NaiveDate) -> String {
+    format!("Matter status report: {matter_id} ({date})")
+}
+
+/// Collect the memo for the `period_days` ending at `now`.
+///
+/// Internal deadlines and internal documents are left out, as are earlier
+/// exports; the memo is client-ready.
+pub fn collect_status_memo(
+    now: DateTime<Utc>,
+    period_days: i64,
+    tasks: &[MatterTaskRecord],
+    documents: &[MatterDocumentRecord],
+    deadlines: &[MatterDeadlineRecord],
+    time_entries: &[TimeEntryRecord],
+    contact_log: &[ContactLogEntry],
+) -> StatusMemo {
+    let since = now - Duration::days(period_days);
+    let until = now + Duration::days(UPCOMING_DAYS);
+    let (period_start, period_end) = (since.date_naive(), now.date_naive());
+    let in_period = |ts: DateTime<Utc>| ts >= since && ts <= now;
+
+    let mut open: Vec<&MatterTaskRecord> = tasks
+        .iter()
+        .filter(|task| {
+            matches!(
+                task.status,
+                MatterTaskStatus::Todo | MatterTaskStatus::InProgress | MatterTaskStatus::Blocked
+            )
+        })
+        .collect();
+    // Dated tasks first, soonest due; undated tasks keep their order.
+    open.sort_by_key(|task| (task.due_at.is_none(), task.due_at));
+    let open_tasks = open
+        .into_iter()
+        .take(MAX_SECTION_ITEMS)
+        .map(|task| MemoTask {
+            title: task.title.clone(),
+            status: task.status.as_str().replace('_', " "),
+            due: task.due_at.map(|due| due.date_naive().to_string()),
+        })
+        .collect();
+
+    let mut recent: Vec<(DateTime<Utc>, MemoDocument)> = documents
+        .iter()
+        .filter(|doc| {
+            doc.category != MatterDocumentCategory::Internal && !doc.path.contains("/exports/")
+        })
+        .filter_map(|doc| {
+            let (at, change) = if in_period(doc.created_at) {
+                (doc.created_at, "added")
+            } else if in_period(doc.updated_at) {
+                (doc.updated_at, "updated")
+            } else {
+                return None;
+            };
+            Some((
+                at,
+                MemoDocument {
+                    date: at.date_naive().to_string(),
+                    name: doc.display_name.clone(),
+                    category: doc.category.as_str().to_string(),
+                    change,
+                },
+            ))
+        })
+        .collect();
+    recent.sort_by_key(|(at, _)| *at);
+
+    let mut upcoming: Vec<&MatterDeadlineRecord> = deadlines
+        .iter()
+        .filter(|d| {
+            d.deadline_type != MatterDeadlineType::Internal
+                && d.completed_at.is_none()
+                && d.due_at >= now
+                && d.due_at <= until
+        })
+        .collect();
+    upcoming.sort_by_key(|d| d.due_at);
+
+    let period_entries: Vec<&TimeEntryRecord> = time_entries
+        .iter()
+        .filter(|entry| entry.entry_date >= period_start && entry.entry_date <= period_end)
+        .collect();
+
+    // Keep the most recent entries when the log is long.
+    let logged: Vec<&ContactLogEntry> = contact_log
+        .iter()
+        .filter(|entry| entry.date >= period_start && entry.date <= period_end)
+        .collect();
+    let communications = logged[logged.len().saturating_sub(MAX_SECTION_ITEMS)..]
+        .iter()
+        .map(|entry| MemoCommunication {
+            date: entry.date.to_string(),
+            with: entry.with.clone(),
+            channel: entry.channel.clone(),
+            summary: entry.summary.clone(),
+        })
+        .collect();
+
+    StatusMemo {
+        period_start,
+        period_end,
+        open_tasks,
+        recent_documents: recent
+            .into_iter()
+            .take(MAX_SECTION_ITEMS)
+            .map(|(_, doc)| doc)
+            .collect(),
+        upcoming_deadlines: upcoming
+            .into_iter()
+            .take(MAX_SECTION_ITEMS)
+            .map(|d| UpcomingDate {
+                date: d.due_at.date_naive().to_string(),
+                title: d.title.clone(),
+            })
+            .collect(),
+        hours: period_entries.iter().map(|entry| entry.hours).sum(),
+        billable_hours: period_entries
+            .iter()
+            .filter(|entry| entry.billable)
+            .map(|entry| entry.hours)
+            .sum(),
+        time_entry_count: period_entries.len(),
+        communications,
+    }
+}
+
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Render a memo as markdown.
+pub fn render_status_memo(
+    matter_id: &str,
+    client_name: Option<&str>,
+    note: Option<&str>,
+    memo: &StatusMemo,
+) -> String {
+    let mut out = format!("# Matter Status Report: {matter_id}\n\n");
+    if let Some(client) = client_name {
+        out.push_str(&format!("Prepared for {}\n", one_line(client)));
+    }
+    out.push_str(&format!(
+        "Period: {} to {}\n\n",
+        memo.period_start, memo.period_end
+    ));
+    if let Some(note) = note.map(str::trim).filter(|note| !note.is_empty()) {
+        out.push_str(note);
+        out.push_str("\n\n");
+    }
+
+    out.push_str("## Open Tasks\n\n");
+    if memo.open_tasks.is_empty() {
+        out.push_str("- No open tasks.\n");
+    }
+    for task in &memo.open_tasks {
+        match &task.due {
+            Some(due) => out.push_str(&format!(
+                "- {} ({}, due {due})\n",
+                one_line(&task.title),
+                task.status
+            )),
+            None => out.push_str(&format!("- {} ({})\n", one_line(&task.title), task.status)),
+        }
+    }
+
+    out.push_str("\n## Recent Documents\n\n");
+    if memo.recent_documents.is_empty() {
+        out.push_str("- No documents added or updated this period.\n");
+    }
+    for doc in &memo.recent_documents {
+        out.push_str(&format!(
+            "- {}: {} {} ({})\n",
+            doc.date,
+            doc.change,
+            one_line(&doc.name),
+            doc.category
+        ));
+    }
+
+    out.push_str("\n## Upcoming Deadlines\n\n");
+    if memo.upcoming_deadlines.is_empty() {
+        out.push_str(&format!(
+            "- No deadlines in the next {UPCOMING_DAYS} days.\n"
+        ));
+    }
+    for deadline in &memo.upcoming_deadlines {
+        out.push_str(&format!(
+            "- {}: {}\n",
+            deadline.date,
+            one_line(&deadline.title)
+        ));
+    }
+
+    out.push_str("\n## Time This Period\n\n");
+    if memo.time_entry_count == 0 {
+        out.push_str("- No time recorded.\n");
+    } else {
+        out.push_str(&format!(
+            "- {} hours recorded across {} entries ({} billable).\n",
+            memo.hours.normalize(),
+            memo.time_entry_count,
+            memo.billable_hours.normalize()
+        ));
+    }
+
+    out.push_str("\n## Recent Communications\n\n");
+    if memo.communications.is_empty() {
+        out.push_str("- No logged communications this period.\n");
+    }
+    for entry in &memo.communications {
+        out.push_str(&format!(
+            "- {}: {} with {}: {}\n",
+            entry.date,
+            one_line(&entry.channel),
+            one_line(&entry.with),
+            one_line(&entry.summary)
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::DocumentReadinessState;
+    use uuid::Uuid;
+
+    fn task(title: &str, status: MatterTaskStatus, due_in_days: Option<i64>) -> MatterTaskRecord {
+        let now = Utc::now();
+        MatterTaskRecord {
+            id: Uuid::new_v4(),
+            user_id: "u".to_string(),
+            matter_id: "demo".to_string(),
+            title: title.to_string(),
+            description: None,
+            status,
+            assignee: None,
+            due_at: due_in_days.map(|days| now + Duration::days(days)),
+            blocked_by: Vec::new(),
+            position: 0,
+            parent_task_id: None,
+            checklist: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn document(name: &str, path: &str, category: MatterDocumentCategory) -> MatterDocumentRecord {
+        let now = Utc::now();
+        MatterDocumentRecord {
+            id: Uuid::new_v4(),
+            user_id: "u".to_string(),
+            matter_id: "demo".to_string(),
+            memory_document_id: Uuid::new_v4(),
+            path: path.to_string(),
+            display_name: name.to_string(),
+            category,
+            readiness_state: DocumentReadinessState::Draft,
+            created_at: now - Duration::days(2),
+            updated_at: now - Duration::days(2),
+        }
+    }
+
+    fn time_entry(hours: i64, billable: bool, days_ago: i64) -> TimeEntryRecord {
+        let now = Utc::now();
+        TimeEntryRecord {
+            id: Uuid::new_v4(),
+            user_id: "u".to_string(),
+            matter_id: "demo".to_string(),
+            timekeeper: "ab".to_string(),
+            description: "Work".to_string(),
+            hours: Decimal::new(hours, 1),
+            hourly_rate: None,
+            task_code: None,
+            activity_code: None,
+            resolved_rate: None,
+            rate_source: None,
+            entry_date: (now - Duration::days(days_ago)).date_naive(),
+            billable,
+            block_billing_flag: false,
+            block_billing_reason: None,
+            billed_invoice_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn memo_collects_period_activity_and_renders_sections() {
+        let now = Utc::now();
+        let memo = collect_status_memo(
+            now,
+            7,
+            &[
+                task("File reply", MatterTaskStatus::InProgress, Some(3)),
+                task("Collect exhibits", MatterTaskStatus::Todo, None),
+                task("Draft outline", MatterTaskStatus::Done, None),
+            ],
+            &[
+                document(
+                    "Statement of claim",
+                    "matters/demo/pleadings/claim.md",
+                    MatterDocumentCategory::Pleading,
+                ),
+                document(
+                    "Strategy notes",
+                    "matters/demo/notes/strategy.md",
+                    MatterDocumentCategory::Internal,
+                ),
+                document(
+                    "Last report",
+                    "matters/demo/exports/status-report-2026-10-01.md",
+                    MatterDocumentCategory::Correspondence,
+                ),
+            ],
+            &[],
+            &[
+                time_entry(15, true, 1),
+                time_entry(5, false, 2),
+                time_entry(40, true, 30),
+            ],
+            &[ContactLogEntry {
+                date: now.date_naive(),
+                with: "client@example.com".to_string(),
+                channel: "Email".to_string(),
+                summary: "Sent \"Update\"".to_string(),
+            }],
+        );
+
+        assert_eq!(
+            memo.open_tasks
+                .iter()
+                .map(|t| t.title.as_str())
+                .collect::<Vec<_>>(),
+            vec!["File reply", "Collect exhibits"]
+        );
+        assert_eq!(memo.recent_documents.len(), 1);
+        assert_eq!(memo.time_entry_count, 2);
+        assert_eq!(memo.hours, Decimal::new(20, 1));
+        assert_eq!(memo.billable_hours, Decimal::new(15, 1));
+
+        let rendered = render_status_memo("demo", Some("Acme Corp"), Some("All on track."), &memo);
+        assert!(rendered.starts_with("# Matter Status Report: demo\n\nPrepared for Acme Corp\n"));
+        assert!(rendered.contains("All on track.\n"));
+        assert!(rendered.contains("- File reply (in progress, due "));
+        assert!(rendered.contains(": added Statement of claim (pleading)\n"));
+        assert!(!rendered.contains("Strategy notes"));
+        assert!(rendered.contains("- 2 hours recorded across 2 entries (1.5 billable).\n"));
+        assert!(rendered.contains(": Email with client@example.com: Sent \"Update\"\n"));
+        assert!(rendered.contains("- No deadlines in the next 30 days.\n"));
+    }
+}
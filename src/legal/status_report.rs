@@ -0,0 +1,462 @@
+//! Weekly client status reports.
+//!
+//! The weekly routine ([`weekly_status_routine`]) asks the agent to draft a
+//! client-facing update per active matter with the `client_status_report`
+//! tool. Drafts are rendered through the docgen template
+//! [`TEMPLATE_NAME`], written under the matter's `communications/` folder,
+//! and queued in [`crate::db::ClientStatusReportStore`]. Nothing reaches the
+//! client until an attorney approves the report in the gateway, which then
+//! hands it to the email tool.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::agent::routine::{
+    NotifyConfig, Routine, RoutineAction, RoutineChain, RoutineGuardrails, Trigger, next_cron_fire,
+};
+use crate::db::{
+    ClientRecord, MatterDeadlineRecord, MatterDeadlineType, MatterDocumentCategory,
+    MatterDocumentRecord, MatterRecord, MatterTaskRecord, MatterTaskStatus,
+};
+use crate::legal::locale::Locale;
+
+/// Docgen template name; a matter or firm template with this name overrides
+/// [`TEMPLATE_BODY`], and its language variants
+/// (`client_status_update.es.md`) are used for clients who prefer them.
+pub const TEMPLATE_NAME: &str = "client_status_update.md";
+
+/// Default client status update template (Tera, rendered with
+/// [`crate::legal::docgen::build_context`]).
+pub const TEMPLATE_BODY: &str = "\
+# Status Update: {{ matter.matter_id }}
+
+Prepared for {{ client.name }}{% if extra.period_start %} · {{ extra.period_start }} to {{ extra.period_end }}{% endif %}
+
+{% if extra.summary %}{{ extra.summary }}
+
+{% endif %}## Recent Activity
+
+{% if extra.recent_activity %}{% for item in extra.recent_activity %}- {{ item.date }}: {{ item.summary }}
+{% endfor %}{% else %}- No reportable activity this period.
+{% endif %}
+## Upcoming Dates
+
+{% if extra.upcoming_dates %}{% for item in extra.upcoming_dates %}- {{ item.date }}: {{ item.title }}
+{% endfor %}{% else %}- No scheduled dates in the coming weeks.
+{% endif %}
+## Next Steps
+
+{% if extra.next_steps %}{% for step in extra.next_steps %}- {{ step }}
+{% endfor %}{% else %}- We will follow up if anything requires your attention.
+{% endif %}
+Please reply to this message with any questions.
+";
+
+/// Name of the routine installed by the status-report routine template.
+pub const ROUTINE_NAME: &str = "weekly-client-status-reports";
+
+/// Mondays at 09:00 (6-field cron).
+pub const ROUTINE_SCHEDULE: &str = "0 0 9 * * MON";
+
+/// Tool used to deliver approved reports.
+pub const EMAIL_TOOL_NAME: &str = "gmail";
+
+const ROUTINE_PROMPT: &str = "\
+Draft this week's client status reports.
+
+1. Call `client_status_report` without a `matter_id` to list active matters and their recent activity, upcoming dates, and open tasks.
+2. For each matter that has a client email on file, call `client_status_report` again with its `matter_id`, a two to four sentence plain-language `summary`, and two to five concrete `next_steps`. When a matter lists a `drafting_instruction`, follow it for the summary and next steps.
+3. Ground every statement in the facts returned by the tool. Do not include legal analysis, privileged strategy, internal notes, or billing details.
+
+Do not send email. Each draft is queued for attorney approval and is only delivered after an attorney approves it. Finish with a list of the drafts you queued and any matters you skipped, with the reason.";
+
+/// Build the weekly status-report routine for `user_id`.
+pub fn weekly_status_routine(user_id: &str) -> Routine {
+    let now = Utc::now();
+    Routine {
+        id: Uuid::new_v4(),
+        name: ROUTINE_NAME.to_string(),
+        description: "Drafts a client status update per active matter for attorney approval"
+            .to_string(),
+        user_id: user_id.to_string(),
+        enabled: true,
+        trigger: Trigger::Cron {
+            schedule: ROUTINE_SCHEDULE.to_string(),
+            timezone: None,
+            jitter_secs: 0,
+        },
+        action: RoutineAction::FullJob {
+            title: "Weekly client status reports".to_string(),
+            description: ROUTINE_PROMPT.to_string(),
+            max_iterations: 40,
+        },
+        guardrails: RoutineGuardrails {
+            cooldown: std::time::Duration::from_secs(6 * 60 * 60),
+            max_concurrent: 1,
+            dedup_window: None,
+            urgent: false,
+            execution: Default::default(),
+        },
+        notify: NotifyConfig::default(),
+        chain: RoutineChain::default(),
+        last_run_at: None,
+        next_fire_at: next_cron_fire(ROUTINE_SCHEDULE).unwrap_or(None),
+        run_count: 0,
+        consecutive_failures: 0,
+        state: serde_json::json!({}),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// Workspace path for the report drafted on `date`.
+pub fn report_path(matter_root: &str, matter_id: &str, date: NaiveDate) -> String {
+    format!(
+        "{}/{matter_id}/communications/status-reports/{date}.md",
+        matter_root.trim_matches('/')
+    )
+}
+
+/// Email subject for a report.
+pub fn report_subject(matter_id: &str, date: NaiveDate) -> String {
+    format!("Status update: {matter_id} (week of {date})")
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ActivityItem {
+    pub date: String,
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpcomingDate {
+    pub date: String,
+    pub title: String,
+}
+
+/// Client-reportable facts for one matter.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StatusReportFacts {
+    pub period_start: String,
+    pub period_end: String,
+    pub recent_activity: Vec<ActivityItem>,
+    pub upcoming_dates: Vec<UpcomingDate>,
+    pub open_tasks: Vec<String>,
+}
+
+/// Collect what happened in the last `lookback_days` and what is due in
+/// the next `lookahead_days`.
+///
+/// Internal deadlines and documents are left out; the report is
+/// client-facing.
+pub fn collect_status_facts(
+    now: DateTime<Utc>,
+    lookback_days: i64,
+    lookahead_days: i64,
+    deadlines: &[MatterDeadlineRecord],
+    tasks: &[MatterTaskRecord],
+    documents: &[MatterDocumentRecord],
+) -> StatusReportFacts {
+    let since = now - Duration::days(lookback_days);
+    let until = now + Duration::days(lookahead_days);
+    let in_period = |ts: DateTime<Utc>| ts >= since && ts <= now;
+
+    let deadlines = deadlines
+        .iter()
+        .filter(|d| d.deadline_type != MatterDeadlineType::Internal);
+
+    let mut activity: Vec<(DateTime<Utc>, String)> = Vec::new();
+    for deadline in deadlines.clone() {
+        if let Some(completed_at) = deadline.completed_at.filter(|ts| in_period(*ts)) {
+            activity.push((completed_at, format!("Completed: {}", deadline.title)));
+        }
+    }
+    for task in tasks {
+        if task.status == MatterTaskStatus::Done && in_period(task.updated_at) {
+            activity.push((task.updated_at, format!("Completed: {}", task.title)));
+        }
+    }
+    for document in documents {
+        if document.category == MatterDocumentCategory::Internal {
+            continue;
+        }
+        if in_period(document.created_at) {
+            activity.push((
+                document.created_at,
+                format!(
+                    "Added {}: {}",
+                    document.category.as_str(),
+                    document.display_name
+                ),
+            ));
+        } else if in_period(document.updated_at) {
+            activity.push((
+                document.updated_at,
+                format!(
+                    "Updated {}: {}",
+                    document.category.as_str(),
+                    document.display_name
+                ),
+            ));
+        }
+    }
+    activity.sort_by_key(|a| a.0);
+
+    let mut upcoming: Vec<&MatterDeadlineRecord> = deadlines
+        .filter(|d| d.completed_at.is_none() && d.due_at >= now && d.due_at <= until)
+        .collect();
+    upcoming.sort_by_key(|d| d.due_at);
+
+    let open_tasks = tasks
+        .iter()
+        .filter(|t| {
+            matches!(
+                t.status,
+                MatterTaskStatus::Todo | MatterTaskStatus::InProgress
+            )
+        })
+        .map(|t| t.title.clone())
+        .collect();
+
+    StatusReportFacts {
+        period_start: since.date_naive().to_string(),
+        period_end: now.date_naive().to_string(),
+        recent_activity: activity
+            .into_iter()
+            .map(|(ts, summary)| ActivityItem {
+                date: ts.date_naive().to_string(),
+                summary,
+            })
+            .collect(),
+        upcoming_dates: upcoming
+            .into_iter()
+            .map(|d| UpcomingDate {
+                date: d.due_at.date_naive().to_string(),
+                title: d.title.clone(),
+            })
+            .collect(),
+        open_tasks,
+    }
+}
+
+/// Render a report draft through the docgen template, formatting dates and
+/// amounts for `locale`.
+pub fn render_status_report(
+    template_body: &str,
+    locale: Locale,
+    matter: &MatterRecord,
+    client: &ClientRecord,
+    facts: &StatusReportFacts,
+    summary: Option<&str>,
+    next_steps: &[String],
+) -> Result<String, String> {
+    let extra = serde_json::json!({
+        "period_start": facts.period_start,
+        "period_end": facts.period_end,
+        "summary": summary,
+        "recent_activity": facts.recent_activity,
+        "upcoming_dates": facts.upcoming_dates,
+        "next_steps": next_steps,
+    });
+    let mut context = crate::legal::docgen::build_context(matter, client, Some(&extra));
+    crate::legal::docgen::insert_locale_context(&mut context, locale);
+    crate::legal::docgen::render_template(template_body, &context)
+}
+
+/// Parameters for the email tool's `send_message` action.
+pub fn email_params(recipient: &str, subject: &str, body: &str) -> serde_json::Value {
+    serde_json::json!({
+        "action": "send_message",
+        "to": recipient,
+        "subject": subject,
+        "body": body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{ClientType, DocumentReadinessState, MatterStatus};
+
+    fn deadline(
+        title: &str,
+        due_at: DateTime<Utc>,
+        completed_at: Option<DateTime<Utc>>,
+    ) -> MatterDeadlineRecord {
+        MatterDeadlineRecord {
+            id: Uuid::new_v4(),
+            user_id: "u1".to_string(),
+            matter_id: "demo".to_string(),
+            title: title.to_string(),
+            deadline_type: MatterDeadlineType::CourtDate,
+            due_at,
+            completed_at,
+            reminder_days: Vec::new(),
+            rule_ref: None,
+            computed_from: None,
+            task_id: None,
+            explanation: None,
+            rule_version: None,
+            is_manual_override: false,
+            override_reason: None,
+            override_by: None,
+            overridden_at: None,
+            is_unsupported: false,
+            created_at: due_at,
+            updated_at: due_at,
+        }
+    }
+
+    fn document(
+        name: &str,
+        category: MatterDocumentCategory,
+        at: DateTime<Utc>,
+    ) -> MatterDocumentRecord {
+        MatterDocumentRecord {
+            id: Uuid::new_v4(),
+            user_id: "u1".to_string(),
+            matter_id: "demo".to_string(),
+            memory_document_id: Uuid::new_v4(),
+            path: format!("matters/demo/{name}"),
+            display_name: name.to_string(),
+            category,
+            readiness_state: DocumentReadinessState::Draft,
+            created_at: at,
+            updated_at: at,
+        }
+    }
+
+    #[test]
+    fn facts_cover_the_period_and_skip_internal_documents() {
+        let now = Utc::now();
+        let deadlines = vec![
+            deadline(
+                "Initial disclosures",
+                now - Duration::days(2),
+                Some(now - Duration::days(2)),
+            ),
+            deadline(
+                "Old hearing",
+                now - Duration::days(30),
+                Some(now - Duration::days(30)),
+            ),
+            deadline("Case management conference", now + Duration::days(10), None),
+            deadline("Trial", now + Duration::days(200), None),
+        ];
+        let documents = vec![
+            document(
+                "Answer",
+                MatterDocumentCategory::Pleading,
+                now - Duration::days(1),
+            ),
+            document(
+                "Strategy memo",
+                MatterDocumentCategory::Internal,
+                now - Duration::days(1),
+            ),
+        ];
+        let facts = collect_status_facts(now, 7, 30, &deadlines, &[], &documents);
+
+        let activity: Vec<&str> = facts
+            .recent_activity
+            .iter()
+            .map(|a| a.summary.as_str())
+            .collect();
+        assert_eq!(
+            activity,
+            vec!["Completed: Initial disclosures", "Added pleading: Answer"]
+        );
+        assert_eq!(facts.upcoming_dates.len(), 1);
+        assert_eq!(facts.upcoming_dates[0].title, "Case management conference");
+    }
+
+    #[test]
+    fn default_template_renders_with_and_without_facts() {
+        let client_id = Uuid::new_v4();
+        let now = Utc::now();
+        let matter = MatterRecord {
+            user_id: "u1".to_string(),
+            matter_id: "demo".to_string(),
+            client_id,
+            status: MatterStatus::Active,
+            stage: None,
+            practice_area: None,
+            jurisdiction: None,
+            opened_at: None,
+            closed_at: None,
+            assigned_to: Vec::new(),
+            custom_fields: serde_json::json!({}),
+            created_at: now,
+            updated_at: now,
+        };
+        let client = ClientRecord {
+            id: client_id,
+            user_id: "u1".to_string(),
+            name: "Acme Corp".to_string(),
+            name_normalized: "acme corp".to_string(),
+            client_type: ClientType::Entity,
+            email: Some("gc@acme.test".to_string()),
+            phone: None,
+            address: None,
+            notes: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let empty = render_status_report(
+            TEMPLATE_BODY,
+            Locale::EnUs,
+            &matter,
+            &client,
+            &StatusReportFacts::default(),
+            None,
+            &[],
+        )
+        .expect("render empty report");
+        assert!(empty.contains("Prepared for Acme Corp"));
+        assert!(empty.contains("No reportable activity this period."));
+
+        let facts = StatusReportFacts {
+            period_start: "2026-01-01".to_string(),
+            period_end: "2026-01-08".to_string(),
+            recent_activity: vec![ActivityItem {
+                date: "2026-01-05".to_string(),
+                summary: "Added pleading: Answer".to_string(),
+            }],
+            upcoming_dates: vec![UpcomingDate {
+                date: "2026-01-20".to_string(),
+                title: "Case management conference".to_string(),
+            }],
+            open_tasks: Vec::new(),
+        };
+        let report = render_status_report(
+            TEMPLATE_BODY,
+            Locale::EnUs,
+            &matter,
+            &client,
+            &facts,
+            Some("We filed the answer."),
+            &["Prepare for the conference".to_string()],
+        )
+        .expect("render report");
+        assert!(report.contains("We filed the answer."));
+        assert!(report.contains("- 2026-01-05: Added pleading: Answer"));
+        assert!(report.contains("- 2026-01-20: Case management conference"));
+        assert!(report.contains("- Prepare for the conference"));
+    }
+
+    #[test]
+    fn routine_template_is_a_weekly_full_job() {
+        let routine = weekly_status_routine("u1");
+        assert_eq!(routine.name, ROUTINE_NAME);
+        assert!(
+            matches!(routine.trigger, Trigger::Cron { ref schedule, .. } if schedule == ROUTINE_SCHEDULE)
+        );
+        assert!(routine.next_fire_at.is_some());
+        let RoutineAction::FullJob { description, .. } = &routine.action else {
+            panic!("expected full job");
+        };
+        assert!(description.contains("Do not send email"));
+    }
+}
@@ -0,0 +1,215 @@
+//! Per-matter storage accounting and soft quota evaluation.
+
+use serde::Serialize;
+
+use crate::config::{LegalConfig, LegalStorageConfig};
+use crate::db::Database;
+use crate::workspace::{Workspace, WorkspaceStorageUsage};
+
+/// Where a matter sits relative to its soft quota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaStatus {
+    /// No soft quota configured.
+    Unlimited,
+    Ok,
+    /// At or past the warning threshold but under the quota.
+    Warning,
+    Exceeded,
+}
+
+/// Result of checking a matter's footprint against the configured soft quota.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QuotaEvaluation {
+    pub status: QuotaStatus,
+    pub used_bytes: u64,
+    pub soft_quota_bytes: Option<u64>,
+    pub warn_at_percent: u8,
+    /// Usage as a whole-number percentage of the quota, when one is set.
+    pub percent_used: Option<u64>,
+}
+
+impl QuotaEvaluation {
+    pub fn should_warn(&self) -> bool {
+        matches!(self.status, QuotaStatus::Warning | QuotaStatus::Exceeded)
+    }
+
+    /// Human-readable warning for API responses, if one is due.
+    pub fn warning_message(&self, matter_id: &str) -> Option<String> {
+        let quota = self.soft_quota_bytes?;
+        let percent = self.percent_used.unwrap_or(0);
+        match self.status {
+            QuotaStatus::Warning => Some(format!(
+                "Matter '{matter_id}' is using {percent}% of its {} soft storage quota",
+                format_bytes(quota)
+            )),
+            QuotaStatus::Exceeded => Some(format!(
+                "Matter '{matter_id}' exceeds its {} soft storage quota ({} used)",
+                format_bytes(quota),
+                format_bytes(self.used_bytes)
+            )),
+            QuotaStatus::Unlimited | QuotaStatus::Ok => None,
+        }
+    }
+}
+
+/// Full storage picture for one matter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MatterStorageReport {
+    pub matter_id: String,
+    pub usage: WorkspaceStorageUsage,
+    pub version_count: u64,
+    pub quota: QuotaEvaluation,
+}
+
+pub fn evaluate_quota(config: &LegalStorageConfig, used_bytes: u64) -> QuotaEvaluation {
+    let Some(quota) = config.soft_quota_bytes.filter(|quota| *quota > 0) else {
+        return QuotaEvaluation {
+            status: QuotaStatus::Unlimited,
+            used_bytes,
+            soft_quota_bytes: None,
+            warn_at_percent: config.warn_at_percent,
+            percent_used: None,
+        };
+    };
+    let percent_used = (u128::from(used_bytes) * 100 / u128::from(quota)) as u64;
+    let status = if used_bytes > quota {
+        QuotaStatus::Exceeded
+    } else if percent_used >= u64::from(config.warn_at_percent) {
+        QuotaStatus::Warning
+    } else {
+        QuotaStatus::Ok
+    };
+    QuotaEvaluation {
+        status,
+        used_bytes,
+        soft_quota_bytes: Some(quota),
+        warn_at_percent: config.warn_at_percent,
+        percent_used: Some(percent_used),
+    }
+}
+
+/// Measure a matter's documents, index chunks, and versions, and evaluate
+/// the result against the soft quota.
+pub async fn matter_storage_report(
+    workspace: &Workspace,
+    store: &dyn Database,
+    legal: &LegalConfig,
+    matter_id: &str,
+) -> Result<MatterStorageReport, String> {
+    let prefix = crate::legal::matter::matter_prefix(legal, matter_id);
+    let usage = workspace
+        .storage_usage(&prefix)
+        .await
+        .map_err(|err| format!("failed to measure matter storage: {err}"))?;
+    let version_count = store
+        .count_matter_document_versions(workspace.user_id(), matter_id)
+        .await
+        .map_err(|err| format!("failed to count document versions: {err}"))?;
+    Ok(MatterStorageReport {
+        matter_id: matter_id.to_string(),
+        quota: evaluate_quota(&legal.storage, usage.total_bytes()),
+        usage,
+        version_count,
+    })
+}
+
+/// Check the matter owning `path` against its soft quota.
+///
+/// Returns `None` when no quota is configured, the path is outside the
+/// matter root, or usage is below the warning threshold. Measurement
+/// failures are logged and swallowed: quotas are advisory.
+pub async fn quota_warning_for_path(
+    workspace: &Workspace,
+    legal: &LegalConfig,
+    path: &str,
+) -> Option<String> {
+    legal.storage.soft_quota_bytes?;
+    let matter_id = crate::legal::workspace_crypto::matter_id_for_path(path, &legal.matter_root)?;
+    let prefix = crate::legal::matter::matter_prefix(legal, &matter_id);
+    let usage = match workspace.storage_usage(&prefix).await {
+        Ok(usage) => usage,
+        Err(err) => {
+            tracing::debug!(matter_id = %matter_id, "matter storage check failed: {err}");
+            return None;
+        }
+    };
+    let evaluation = evaluate_quota(&legal.storage, usage.total_bytes());
+    if !evaluation.should_warn() {
+        return None;
+    }
+    tracing::warn!(
+        matter_id = %matter_id,
+        used_bytes = evaluation.used_bytes,
+        soft_quota_bytes = ?evaluation.soft_quota_bytes,
+        "Matter storage is approaching or over its soft quota"
+    );
+    crate::legal::audit::record(
+        "matter_storage_quota_warning",
+        serde_json::json!({
+            "matter_id": matter_id,
+            "status": evaluation.status,
+            "used_bytes": evaluation.used_bytes,
+            "soft_quota_bytes": evaluation.soft_quota_bytes,
+        }),
+    );
+    evaluation.warning_message(&matter_id)
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const MB: u64 = 1024 * 1024;
+    const GB: u64 = 1024 * MB;
+    if bytes >= GB {
+        format!("{:.1} GB", bytes as f64 / GB as f64)
+    } else if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(quota: Option<u64>, warn_at_percent: u8) -> LegalStorageConfig {
+        LegalStorageConfig {
+            soft_quota_bytes: quota,
+            warn_at_percent,
+        }
+    }
+
+    #[test]
+    fn evaluate_quota_without_limit_is_unlimited() {
+        let eval = evaluate_quota(&config(None, 80), 10_000);
+        assert_eq!(eval.status, QuotaStatus::Unlimited);
+        assert_eq!(eval.percent_used, None);
+        assert!(!eval.should_warn());
+        assert!(eval.warning_message("demo").is_none());
+    }
+
+    #[test]
+    fn evaluate_quota_thresholds() {
+        let cfg = config(Some(1_000), 80);
+        assert_eq!(evaluate_quota(&cfg, 799).status, QuotaStatus::Ok);
+        assert_eq!(evaluate_quota(&cfg, 800).status, QuotaStatus::Warning);
+        assert_eq!(evaluate_quota(&cfg, 1_000).status, QuotaStatus::Warning);
+        assert_eq!(evaluate_quota(&cfg, 1_001).status, QuotaStatus::Exceeded);
+    }
+
+    #[test]
+    fn warning_message_mentions_matter_and_quota() {
+        let cfg = config(Some(2 * 1024 * 1024), 50);
+        let warning = evaluate_quota(&cfg, 1024 * 1024)
+            .warning_message("acme")
+            .expect("warning");
+        assert!(warning.contains("acme"));
+        assert!(warning.contains("50%"));
+        assert!(warning.contains("2.0 MB"));
+
+        let exceeded = evaluate_quota(&cfg, 3 * 1024 * 1024)
+            .warning_message("acme")
+            .expect("exceeded");
+        assert!(exceeded.contains("exceeds"));
+    }
+}
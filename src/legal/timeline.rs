@@ -0,0 +1,215 @@
+//! Matter timeline: one date-ordered event list merged from chronology
+//! facts, the docket calendar, deadlines, communications, and document
+//! filing dates, for the web UI's visual timeline and mediation exports.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::legal::markdown::table_cell;
+
+/// Where a timeline event came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineEventType {
+    /// Dated fact from the chronology store.
+    Fact,
+    /// Row of the matter's `deadlines/calendar.md` docket.
+    Docket,
+    /// Calendared deadline from the deadline engine.
+    Deadline,
+    /// Contact log entry.
+    Communication,
+    /// Document added to the matter.
+    Document,
+}
+
+impl TimelineEventType {
+    pub const ALL: [Self; 5] = [
+        Self::Fact,
+        Self::Docket,
+        Self::Deadline,
+        Self::Communication,
+        Self::Document,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Fact => "fact",
+            Self::Docket => "docket",
+            Self::Deadline => "deadline",
+            Self::Communication => "communication",
+            Self::Document => "document",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "fact" | "facts" => Some(Self::Fact),
+            "docket" => Some(Self::Docket),
+            "deadline" | "deadlines" => Some(Self::Deadline),
+            "communication" | "communications" => Some(Self::Communication),
+            "document" | "documents" => Some(Self::Document),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Fact => "Fact",
+            Self::Docket => "Docket",
+            Self::Deadline => "Deadline",
+            Self::Communication => "Communication",
+            Self::Document => "Document",
+        }
+    }
+}
+
+/// Parse a comma-separated `types` filter; empty means every type.
+pub fn parse_event_types(raw: Option<&str>) -> Result<Vec<TimelineEventType>, String> {
+    let Some(raw) = raw.map(str::trim).filter(|raw| !raw.is_empty()) else {
+        return Ok(TimelineEventType::ALL.to_vec());
+    };
+    let mut types = Vec::new();
+    for part in raw.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let kind = TimelineEventType::parse(part).ok_or_else(|| {
+            format!(
+                "unknown timeline type '{part}' (expected fact, docket, deadline, communication, or document)"
+            )
+        })?;
+        if !types.contains(&kind) {
+            types.push(kind);
+        }
+    }
+    Ok(types)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimelineEvent {
+    pub date: NaiveDate,
+    /// Time of day, when the source records one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub at: Option<DateTime<Utc>>,
+    #[serde(rename = "type")]
+    pub event_type: TimelineEventType,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Workspace path or table the event was read from.
+    pub source: String,
+    /// Record id within `source`, for database-backed events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
+    /// Location inside the source document, e.g. `line 12`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_locator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+/// Order events by date, then time of day (untimed events first), then
+/// type and title, so the order is stable across requests.
+pub fn sort_timeline(events: &mut [TimelineEvent]) {
+    events.sort_by(|a, b| {
+        a.date
+            .cmp(&b.date)
+            .then_with(|| a.at.cmp(&b.at))
+            .then_with(|| a.event_type.cmp(&b.event_type))
+            .then_with(|| a.title.cmp(&b.title))
+    });
+}
+
+/// Workspace path for a timeline export generated at `timestamp`
+/// (`YYYYMMDD-HHMMSS`).
+pub fn timeline_export_path(matter_prefix: &str, timestamp: &str) -> String {
+    format!("{matter_prefix}/exports/timeline-{timestamp}.md")
+}
+
+/// Markdown timeline table for mediation briefs and presentations.
+pub fn render_timeline_markdown(
+    matter_label: &str,
+    events: &[TimelineEvent],
+    generated_at: DateTime<Utc>,
+) -> String {
+    let mut out = format!(
+        "# Timeline: {matter_label}\n\nGenerated: {}\n\n",
+        generated_at.to_rfc3339()
+    );
+    if events.is_empty() {
+        out.push_str("- No dated events.\n");
+        return out;
+    }
+    out.push_str("| Date | Type | Event | Details | Source |\n|---|---|---|---|---|\n");
+    for event in events {
+        let date = match event.at {
+            Some(at) => at.format("%Y-%m-%d %H:%M").to_string(),
+            None => event.date.to_string(),
+        };
+        let mut details = event.detail.clone().unwrap_or_default();
+        if let Some(status) = &event.status {
+            if !details.is_empty() {
+                details.push_str("; ");
+            }
+            details.push_str(status);
+        }
+        let source = match &event.source_locator {
+            Some(locator) => format!("{} ({locator})", event.source),
+            None => event.source.clone(),
+        };
+        out.push_str(&format!(
+            "| {date} | {} | {} | {} | {} |\n",
+            event.event_type.label(),
+            table_cell(&event.title),
+            table_cell(&details),
+            table_cell(&source)
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(date: &str, event_type: TimelineEventType, title: &str) -> TimelineEvent {
+        TimelineEvent {
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            at: None,
+            event_type,
+            title: title.to_string(),
+            detail: None,
+            source: "matters/demo/notes.md".to_string(),
+            source_id: None,
+            source_locator: None,
+            status: None,
+        }
+    }
+
+    #[test]
+    fn events_sort_by_date_then_time_then_type() {
+        let mut timed = event("2025-03-01", TimelineEventType::Communication, "Call");
+        timed.at = Some("2025-03-01T15:00:00Z".parse().unwrap());
+        let mut events = vec![
+            timed,
+            event("2025-03-02", TimelineEventType::Fact, "Later fact"),
+            event("2025-03-01", TimelineEventType::Document, "Complaint"),
+            event("2025-03-01", TimelineEventType::Fact, "Accident"),
+        ];
+        sort_timeline(&mut events);
+        let titles: Vec<&str> = events.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["Accident", "Complaint", "Call", "Later fact"]);
+
+        let markdown = render_timeline_markdown("Demo v. Acme", &events, Utc::now());
+        assert!(markdown.contains("| 2025-03-01 | Fact | Accident |  | matters/demo/notes.md |"));
+        assert!(markdown.contains("| 2025-03-01 15:00 | Communication | Call |"));
+    }
+
+    #[test]
+    fn type_filters_accept_plurals_and_reject_unknown_types() {
+        assert_eq!(
+            parse_event_types(Some("facts, deadline,facts")).unwrap(),
+            vec![TimelineEventType::Fact, TimelineEventType::Deadline]
+        );
+        assert_eq!(parse_event_types(None).unwrap().len(), 5);
+        assert!(parse_event_types(Some("emails")).is_err());
+    }
+}
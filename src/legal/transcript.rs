@@ -0,0 +1,484 @@
+//! Deposition transcripts: page:line parsing and testimony citations.
+//!
+//! Court-reporter transcripts number every line within a page (usually 25
+//! lines) and mark pages with a form feed, a bare page number, or a
+//! `Page 45` header. Ingestion parses that structure and stores a
+//! normalized copy in which every line carries its `page:line` address
+//! (`45:12 Q. Where were you?`), so each search chunk of the document is
+//! addressable. Quotes are cited in the `Smith Dep. 45:12-18` form.
+
+use std::fmt;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Matter folder normalized transcripts are filed under.
+pub const TRANSCRIPT_FOLDER: &str = "transcripts";
+/// Front-matter `kind` of a stored normalized transcript.
+pub const TRANSCRIPT_KIND: &str = "deposition_transcript";
+
+/// Highest line number read as a numbered transcript line.
+const MAX_LINE_NUMBER: u32 = 30;
+/// Highest number a bare numbered blank line can carry; a bare number past
+/// this starts a new page.
+const MAX_BLANK_LINE_NUMBER: u32 = 25;
+/// Numbered lines needed before text is treated as a transcript.
+const MIN_NUMBERED_LINES: usize = 5;
+/// Lines scanned for the `DEPOSITION OF ...` caption.
+const WITNESS_SCAN_LINES: usize = 120;
+
+static PAGE_HEADER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^\s*\[?page\s+(\d{1,5})(?:\s+of\s+\d{1,5})?\]?\s*$")
+        .expect("valid transcript page header regex")
+});
+static BARE_NUMBER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(\d{1,5})\s*$").expect("valid transcript bare number regex"));
+static NUMBERED_LINE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(\d{1,2})\s+(\S.*?)\s*$").expect("valid transcript numbered line regex")
+});
+static NORMALIZED_LINE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\d{1,5}):(\d{1,2})(?:\s(.*))?$").expect("valid normalized transcript regex")
+});
+static WITNESS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\bdeposition\s+of\s+(.+)$").expect("valid deposition caption regex")
+});
+
+/// A `page:line` address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct LineRef {
+    pub page: u32,
+    pub line: u32,
+}
+
+impl LineRef {
+    /// Parse `45:12`.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let (page, line) = raw.trim().split_once(':')?;
+        let page: u32 = page.trim().parse().ok()?;
+        let line: u32 = line.trim().parse().ok()?;
+        (page > 0 && line > 0).then_some(Self { page, line })
+    }
+}
+
+impl fmt::Display for LineRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.page, self.line)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TranscriptLine {
+    pub number: u32,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TranscriptPage {
+    pub number: u32,
+    pub lines: Vec<TranscriptLine>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Transcript {
+    /// Deponent named in the caption or the normalized front matter.
+    pub witness: Option<String>,
+    pub pages: Vec<TranscriptPage>,
+}
+
+impl Transcript {
+    pub fn line_count(&self) -> usize {
+        self.pages.iter().map(|page| page.lines.len()).sum()
+    }
+
+    /// Every line with its address, in transcript order.
+    pub fn lines(&self) -> impl Iterator<Item = (LineRef, &str)> {
+        self.pages.iter().flat_map(|page| {
+            page.lines.iter().map(move |line| {
+                (
+                    LineRef {
+                        page: page.number,
+                        line: line.number,
+                    },
+                    line.text.as_str(),
+                )
+            })
+        })
+    }
+
+    /// Lines from `start` through `end`, inclusive.
+    pub fn excerpt(&self, start: LineRef, end: LineRef) -> Result<Vec<(LineRef, &str)>, String> {
+        if end < start {
+            return Err(format!("range ends ({end}) before it starts ({start})"));
+        }
+        if !self.lines().any(|(at, _)| at == start) {
+            return Err(format!("transcript has no line {start}"));
+        }
+        Ok(self
+            .lines()
+            .filter(|(at, _)| *at >= start && *at <= end)
+            .collect())
+    }
+
+    /// Lines containing `query`, case-insensitively.
+    pub fn search(&self, query: &str) -> Vec<(LineRef, &str)> {
+        let needle = query.trim().to_lowercase();
+        if needle.is_empty() {
+            return Vec::new();
+        }
+        self.lines()
+            .filter(|(_, text)| text.to_lowercase().contains(&needle))
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptFrontMatter {
+    #[serde(default)]
+    kind: Option<String>,
+    #[serde(default)]
+    witness: Option<String>,
+}
+
+fn title_case(raw: &str) -> String {
+    raw.split_whitespace()
+        .map(|word| {
+            let lower = word.to_lowercase();
+            let mut chars = lower.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Deponent named by a `DEPOSITION OF JOHN A. SMITH, taken ...` caption.
+fn witness_from_caption(raw: &str) -> Option<String> {
+    raw.lines().take(WITNESS_SCAN_LINES).find_map(|line| {
+        let text = NUMBERED_LINE_RE
+            .captures(line)
+            .map(|captures| captures[2].to_string())
+            .unwrap_or_else(|| line.trim().to_string());
+        let rest = WITNESS_RE.captures(&text)?.get(1)?.as_str().to_string();
+        let lower = rest.to_ascii_lowercase();
+        let end = [",", " taken", " volume", " on ", "  "]
+            .iter()
+            .filter_map(|stop| lower.find(stop))
+            .min()
+            .unwrap_or(rest.len());
+        let name = rest[..end].trim().trim_end_matches(['.', ':']);
+        if name.is_empty() || name.chars().any(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        Some(if name.chars().any(|c| c.is_lowercase()) {
+            name.to_string()
+        } else {
+            title_case(name)
+        })
+    })
+}
+
+struct PageBuilder {
+    pages: Vec<TranscriptPage>,
+    current: Option<TranscriptPage>,
+    page_break: bool,
+    numbered_lines: usize,
+}
+
+impl PageBuilder {
+    fn last_line(&self) -> u32 {
+        self.current
+            .as_ref()
+            .and_then(|page| page.lines.last())
+            .map_or(0, |line| line.number)
+    }
+
+    fn next_page_number(&self) -> u32 {
+        self.current
+            .as_ref()
+            .or(self.pages.last())
+            .map_or(1, |page| page.number + 1)
+    }
+
+    fn start_page(&mut self, number: u32) {
+        if let Some(page) = self.current.take()
+            && !page.lines.is_empty()
+        {
+            self.pages.push(page);
+        }
+        self.current = Some(TranscriptPage {
+            number,
+            lines: Vec::new(),
+        });
+        self.page_break = false;
+    }
+
+    fn push_line(&mut self, number: u32, text: &str) {
+        if self.page_break || self.current.is_none() || number <= self.last_line() {
+            let next = self.next_page_number();
+            self.start_page(next);
+        }
+        if let Some(page) = self.current.as_mut() {
+            page.lines.push(TranscriptLine {
+                number,
+                text: text.to_string(),
+            });
+            self.numbered_lines += 1;
+        }
+    }
+
+    fn finish(mut self) -> Vec<TranscriptPage> {
+        self.start_page(0);
+        self.pages
+    }
+}
+
+fn split_front_matter(raw: &str) -> (Option<&str>, &str) {
+    if let Some(rest) = raw.strip_prefix("---\n")
+        && let Some(end) = rest.find("\n---\n")
+    {
+        return (Some(&rest[..end]), &rest[end + 5..]);
+    }
+    (None, raw)
+}
+
+/// Read a stored normalized transcript (`page:line text` lines).
+fn parse_normalized(raw: &str) -> Option<Transcript> {
+    let (front_matter, body) = split_front_matter(raw);
+    let front: TranscriptFrontMatter = serde_yml::from_str(front_matter?).ok()?;
+    if front.kind.as_deref() != Some(TRANSCRIPT_KIND) {
+        return None;
+    }
+    let mut builder = PageBuilder {
+        pages: Vec::new(),
+        current: None,
+        page_break: false,
+        numbered_lines: 0,
+    };
+    for line in body.lines() {
+        let Some(captures) = NORMALIZED_LINE_RE.captures(line) else {
+            continue;
+        };
+        let page: u32 = captures[1].parse().ok()?;
+        let number: u32 = captures[2].parse().ok()?;
+        if builder.current.as_ref().map(|p| p.number) != Some(page) {
+            builder.start_page(page);
+        }
+        builder.push_line(number, captures.get(3).map_or("", |m| m.as_str()));
+    }
+    if builder.numbered_lines == 0 {
+        return None;
+    }
+    Some(Transcript {
+        witness: front.witness,
+        pages: builder.finish(),
+    })
+}
+
+/// Parse a transcript, either a court reporter's text export or a stored
+/// normalized copy. Unnumbered lines (running headers, reporter footers)
+/// are dropped.
+pub fn parse_transcript(raw: &str) -> Result<Transcript, String> {
+    let raw = raw.replace("\r\n", "\n");
+    if let Some(transcript) = parse_normalized(&raw) {
+        return Ok(transcript);
+    }
+
+    let mut builder = PageBuilder {
+        pages: Vec::new(),
+        current: None,
+        page_break: false,
+        numbered_lines: 0,
+    };
+    for (i, segment) in raw.split('\u{c}').enumerate() {
+        if i > 0 {
+            builder.page_break = true;
+        }
+        for line in segment.lines() {
+            if let Some(captures) = PAGE_HEADER_RE.captures(line) {
+                builder.start_page(captures[1].parse().unwrap_or_default());
+            } else if let Some(captures) = BARE_NUMBER_RE.captures(line) {
+                let number: u32 = captures[1].parse().unwrap_or_default();
+                let is_blank_line = !builder.page_break
+                    && builder.current.is_some()
+                    && number == builder.last_line() + 1
+                    && number <= MAX_BLANK_LINE_NUMBER;
+                if is_blank_line {
+                    builder.push_line(number, "");
+                } else if number > 0 {
+                    builder.start_page(number);
+                }
+            } else if let Some(captures) = NUMBERED_LINE_RE.captures(line) {
+                let number: u32 = captures[1].parse().unwrap_or_default();
+                if (1..=MAX_LINE_NUMBER).contains(&number) {
+                    builder.push_line(number, &captures[2]);
+                }
+            }
+        }
+    }
+    if builder.numbered_lines < MIN_NUMBERED_LINES {
+        return Err("no page:line structure found; expected numbered transcript lines".to_string());
+    }
+    Ok(Transcript {
+        witness: witness_from_caption(&raw),
+        pages: builder.finish(),
+    })
+}
+
+/// Surname used in citations: `John A. Smith, Jr.` cites as `Smith`.
+pub fn citation_name(witness: &str) -> String {
+    const SUFFIXES: [&str; 7] = ["jr", "sr", "ii", "iii", "iv", "esq", "md"];
+    let name = witness.split(',').next().unwrap_or(witness);
+    name.split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric() && c != '\''))
+        .rfind(|word| !word.is_empty() && !SUFFIXES.contains(&word.to_ascii_lowercase().as_str()))
+        .unwrap_or("Witness")
+        .to_string()
+}
+
+/// `Smith Dep. 45:12`, `Smith Dep. 45:12-18`, or `Smith Dep. 45:12-46:3`.
+pub fn format_citation(witness: &str, start: LineRef, end: LineRef) -> String {
+    let name = citation_name(witness);
+    if start == end {
+        format!("{name} Dep. {start}")
+    } else if start.page == end.page {
+        format!("{name} Dep. {start}-{}", end.line)
+    } else {
+        format!("{name} Dep. {start}-{end}")
+    }
+}
+
+/// Workspace path of the normalized copy of an uploaded transcript.
+pub fn transcript_path(matter_prefix: &str, file_name: &str) -> String {
+    let stem = file_name
+        .rsplit('/')
+        .next()
+        .unwrap_or(file_name)
+        .rsplit_once('.')
+        .map_or(file_name, |(stem, _)| stem);
+    let slug: String = stem
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let slug = slug.trim_matches('-');
+    let slug = if slug.is_empty() { "transcript" } else { slug };
+    format!("{matter_prefix}/{TRANSCRIPT_FOLDER}/{slug}.md")
+}
+
+/// Normalized transcript document: front matter, then one `## Page N`
+/// section per page with `page:line` addressed lines.
+pub fn render_transcript(transcript: &Transcript, source_name: &str) -> String {
+    let witness = transcript.witness.as_deref();
+    let mut out = format!("---\nkind: {TRANSCRIPT_KIND}\n");
+    if let Some(witness) = witness {
+        out.push_str(&format!("witness: {}\n", serde_json::json!(witness)));
+        out.push_str(&format!(
+            "citation: {}\n",
+            serde_json::json!(format!("{} Dep.", citation_name(witness)))
+        ));
+    }
+    out.push_str(&format!(
+        "source: {}\npages: {}\nlines: {}\n---\n\n",
+        serde_json::json!(source_name),
+        transcript.pages.len(),
+        transcript.line_count()
+    ));
+    out.push_str(&match witness {
+        Some(witness) => format!("# Deposition of {witness}\n"),
+        None => "# Deposition Transcript\n".to_string(),
+    });
+    for page in &transcript.pages {
+        out.push_str(&format!("\n## Page {}\n\n", page.number));
+        for line in &page.lines {
+            if line.text.is_empty() {
+                out.push_str(&format!("{}:{}\n", page.number, line.number));
+            } else {
+                out.push_str(&format!("{}:{} {}\n", page.number, line.number, line.text));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reporter_export() -> String {
+        let mut raw = String::from(
+            "                                                 1\n\
+             1   UNITED STATES DISTRICT COURT\n\
+             2   VIDEOTAPED DEPOSITION OF JOHN A. SMITH, JR., taken on March 3, 2025\n",
+        );
+        for n in 3..=25 {
+            raw.push_str(&format!("{n}\n"));
+        }
+        raw.push_str("      Veritext Legal Solutions\n\u{c}");
+        raw.push_str("                                                 45\n");
+        raw.push_str(" 1        Q.   Where were you on the night of May 1?\n");
+        raw.push_str(" 2        A.   At the warehouse.\n");
+        raw.push_str(" 3\n");
+        raw.push_str(" 4        Q.   Who else was there?\n");
+        raw.push_str("Page 46\n");
+        raw.push_str(" 1        A.   Nobody.\n");
+        raw
+    }
+
+    #[test]
+    fn reporter_exports_parse_into_pages_and_lines() {
+        let transcript = parse_transcript(&reporter_export()).expect("transcript");
+        assert_eq!(transcript.witness.as_deref(), Some("John A. Smith"));
+        let numbers: Vec<u32> = transcript.pages.iter().map(|page| page.number).collect();
+        assert_eq!(numbers, vec![1, 45, 46]);
+        assert_eq!(transcript.pages[0].lines.len(), 25);
+        assert_eq!(transcript.pages[1].lines[1].text, "A.   At the warehouse.");
+        assert_eq!(transcript.pages[1].lines[2].text, "");
+
+        let stored = render_transcript(&transcript, "smith_depo.txt");
+        assert!(stored.contains("citation: \"Smith Dep.\""));
+        assert!(stored.contains("\n45:2 A.   At the warehouse.\n"));
+        assert_eq!(parse_transcript(&stored).expect("normalized"), transcript);
+
+        assert!(parse_transcript("Just a letter.\nNo numbering here.").is_err());
+    }
+
+    #[test]
+    fn excerpts_are_cited_by_page_and_line() {
+        let transcript = parse_transcript(&reporter_export()).expect("transcript");
+        let start = LineRef::parse("45:1").unwrap();
+        let end = LineRef::parse("46:1").unwrap();
+        let excerpt = transcript.excerpt(start, end).expect("excerpt");
+        assert_eq!(excerpt.len(), 5);
+        assert_eq!(
+            format_citation("John A. Smith, Jr.", start, end),
+            "Smith Dep. 45:1-46:1"
+        );
+        assert_eq!(
+            format_citation("Smith", start, LineRef { page: 45, line: 4 }),
+            "Smith Dep. 45:1-4"
+        );
+        assert_eq!(format_citation("Smith", end, end), "Smith Dep. 46:1");
+        assert!(transcript.excerpt(end, start).is_err());
+        assert!(
+            transcript
+                .excerpt(LineRef { page: 9, line: 1 }, end)
+                .is_err()
+        );
+        assert_eq!(
+            transcript.search("WAREHOUSE"),
+            vec![(LineRef { page: 45, line: 2 }, "A.   At the warehouse.")]
+        );
+        assert_eq!(
+            transcript_path("matters/demo", "Smith Depo (Vol 1).txt"),
+            "matters/demo/transcripts/smith-depo--vol-1.md"
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Machine translation of foreign-language exhibits.
+//!
+//! A machine translation is written beside its source document (see
+//! [`machine_translation_path`]) under a banner marking it as an uncertified
+//! draft. Provenance and the certified-translation workflow are tracked in
+//! [`crate::db::DocumentTranslationStore`].
+
+use crate::error::LlmError;
+use std::time::Duration;
+
+use crate::llm::{CacheControl, ChatMessage, CompletionRequest, LlmProvider, LlmTask};
+
+/// Characters of source text sent to the LLM per request.
+const MAX_CHUNK_CHARS: usize = 6_000;
+
+/// Translations of unchanged text are reused for a week.
+const TRANSLATION_CACHE_TTL: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// Suffix that marks a workspace file as a machine translation.
+pub const MACHINE_TRANSLATION_SUFFIX: &str = ".machine-translation.md";
+
+/// Normalize a BCP 47-style language tag (`es`, `pt-BR`, `zh-Hant`).
+///
+/// Returns `None` for anything that is not a 2-3 letter primary subtag
+/// followed by optional 2-8 character alphanumeric subtags.
+pub fn normalize_language_tag(raw: &str) -> Option<String> {
+    let mut parts = raw.trim().split(['-', '_']);
+    let primary = parts.next()?;
+    if !(2..=3).contains(&primary.len()) || !primary.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let mut tag = primary.to_ascii_lowercase();
+    for part in parts {
+        if !(2..=8).contains(&part.len()) || !part.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+        tag.push('-');
+        // Region subtags are conventionally upper case, scripts title case.
+        match part.len() {
+            2 => tag.push_str(&part.to_ascii_uppercase()),
+            4 => {
+                let mut chars = part.chars();
+                if let Some(first) = chars.next() {
+                    tag.push(first.to_ascii_uppercase());
+                }
+                tag.push_str(&chars.as_str().to_ascii_lowercase());
+            }
+            _ => tag.push_str(&part.to_ascii_lowercase()),
+        }
+    }
+    Some(tag)
+}
+
+/// Whether `path` is a machine translation produced by this module.
+pub fn is_machine_translation_path(path: &str) -> bool {
+    path.ends_with(MACHINE_TRANSLATION_SUFFIX)
+}
+
+/// Workspace path for the machine translation of `source_path`, stored in
+/// the same folder: `exhibits/contrato.md` -> `exhibits/contrato.en.machine-translation.md`.
+pub fn machine_translation_path(source_path: &str, target_language: &str) -> String {
+    let stem = source_path.strip_suffix(".md").unwrap_or(source_path);
+    format!("{stem}.{target_language}{MACHINE_TRANSLATION_SUFFIX}")
+}
+
+/// Split content into paragraph-aligned chunks of at most
+/// [`MAX_CHUNK_CHARS`] characters. Oversized paragraphs are split on
+/// character boundaries.
+fn split_for_translation(content: &str) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for paragraph in content.split("\n\n") {
+        if paragraph.trim().is_empty() {
+            continue;
+        }
+        if !current.is_empty()
+            && current.chars().count() + paragraph.chars().count() + 2 > MAX_CHUNK_CHARS
+        {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if paragraph.chars().count() > MAX_CHUNK_CHARS {
+            let chars: Vec<char> = paragraph.chars().collect();
+            for piece in chars.chunks(MAX_CHUNK_CHARS) {
+                chunks.push(piece.iter().collect());
+            }
+            continue;
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(paragraph);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+fn translation_prompt(chunk: &str, source_language: Option<&str>, target_language: &str) -> String {
+    let source = source_language
+        .map(|lang| format!("from `{lang}` "))
+        .unwrap_or_default();
+    format!(
+        "Translate the following legal document excerpt {source}into `{target_language}`.\n\
+         Preserve paragraph breaks, numbering, headings, defined terms, names, dates, and \
+         figures exactly. Do not summarize, omit, or add content. Mark illegible or \
+         untranslatable passages as [untranslatable: ...]. Return only the translation.\n\n\
+         ---\n{chunk}\n---"
+    )
+}
+
+/// Translate `content` with the LLM, chunk by chunk.
+pub async fn translate_text(
+    llm: &dyn LlmProvider,
+    content: &str,
+    source_language: Option<&str>,
+    target_language: &str,
+) -> Result<String, LlmError> {
+    let mut translated = Vec::new();
+    for chunk in split_for_translation(content) {
+        let request = CompletionRequest::new(vec![ChatMessage::user(translation_prompt(
+            &chunk,
+            source_language,
+            target_language,
+        ))])
+        .with_temperature(0.0)
+        .with_task(LlmTask::Drafting)
+        .with_cache_control(CacheControl::Cache {
+            ttl: Some(TRANSLATION_CACHE_TTL),
+        });
+        let response = llm.complete(request).await?;
+        translated.push(response.content.trim().to_string());
+    }
+    Ok(translated.join("\n\n"))
+}
+
+/// Render the stored machine translation with its provenance banner.
+pub fn render_machine_translation(
+    source_path: &str,
+    source_language: Option<&str>,
+    target_language: &str,
+    model: &str,
+    translated: &str,
+) -> String {
+    let languages = match source_language {
+        Some(source) => format!("{source} → {target_language}"),
+        None => format!("→ {target_language}"),
+    };
+    format!(
+        "> **Machine translation — uncertified draft.** Not for filing or reliance until a \
+         certified translation supersedes it.\n\
+         > Source: `{source_path}` ({languages}) · Model: {model} · Generated: {}\n\n{translated}\n",
+        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::StubLlm;
+
+    #[test]
+    fn language_tags_are_normalized_or_rejected() {
+        assert_eq!(normalize_language_tag("ES").as_deref(), Some("es"));
+        assert_eq!(normalize_language_tag("pt_br").as_deref(), Some("pt-BR"));
+        assert_eq!(
+            normalize_language_tag("zh-hant").as_deref(),
+            Some("zh-Hant")
+        );
+        assert_eq!(normalize_language_tag("english"), None);
+        assert_eq!(normalize_language_tag("en-"), None);
+        assert_eq!(normalize_language_tag("../x"), None);
+    }
+
+    #[test]
+    fn translation_is_stored_beside_source() {
+        assert_eq!(
+            machine_translation_path("matters/acme/exhibits/contrato.md", "en"),
+            "matters/acme/exhibits/contrato.en.machine-translation.md"
+        );
+        assert_eq!(
+            machine_translation_path("matters/acme/exhibits/scan.pdf", "fr"),
+            "matters/acme/exhibits/scan.pdf.fr.machine-translation.md"
+        );
+        assert!(is_machine_translation_path(
+            "matters/acme/exhibits/contrato.en.machine-translation.md"
+        ));
+    }
+
+    #[test]
+    fn long_documents_split_on_paragraphs() {
+        let paragraph = "a".repeat(MAX_CHUNK_CHARS / 2 - 10);
+        let content = format!("{paragraph}\n\n{paragraph}\n\n{paragraph}");
+        let chunks = split_for_translation(&content);
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks.iter().all(|c| c.chars().count() <= MAX_CHUNK_CHARS));
+
+        let oversized = "b".repeat(MAX_CHUNK_CHARS + 10);
+        assert_eq!(split_for_translation(&oversized).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn translate_text_calls_llm_per_chunk() {
+        let llm = StubLlm::new("Translated.");
+        let paragraph = "c".repeat(MAX_CHUNK_CHARS - 10);
+        let content = format!("{paragraph}\n\n{paragraph}");
+        let out = translate_text(&llm, &content, Some("es"), "en")
+            .await
+            .expect("translate");
+        assert_eq!(out, "Translated.\n\nTranslated.");
+        assert_eq!(llm.calls(), 2);
+    }
+}
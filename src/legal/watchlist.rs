@@ -0,0 +1,110 @@
+//! Adverse-party watchlist screening.
+//!
+//! Watched names are compared in their conflict-normalized form
+//! ([`normalize_party_name`]): a client or party name matches when it
+//! normalizes to the same string, and document text matches when the watched
+//! name appears as a whole-word run.
+
+use serde::Serialize;
+
+use crate::db::{WatchlistCategory, WatchlistEntryRecord, normalize_party_name};
+
+/// One watched party found in a screened name or document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WatchlistMatch {
+    pub entry_id: String,
+    pub watched_name: String,
+    pub category: WatchlistCategory,
+    pub reason: Option<String>,
+    /// The screened name that matched, or `None` for a document scan.
+    pub matched_name: Option<String>,
+}
+
+fn watchlist_match(entry: &WatchlistEntryRecord, matched_name: Option<&str>) -> WatchlistMatch {
+    WatchlistMatch {
+        entry_id: entry.id.to_string(),
+        watched_name: entry.name.clone(),
+        category: entry.category,
+        reason: entry.reason.clone(),
+        matched_name: matched_name.map(str::to_string),
+    }
+}
+
+/// Watched parties among `names` (e.g. a new client and its adversaries).
+pub fn screen_names(entries: &[WatchlistEntryRecord], names: &[String]) -> Vec<WatchlistMatch> {
+    let mut matches = Vec::new();
+    for name in names {
+        let normalized = normalize_party_name(name);
+        if normalized.is_empty() {
+            continue;
+        }
+        for entry in entries {
+            if entry.name_normalized == normalized {
+                matches.push(watchlist_match(entry, Some(name)));
+            }
+        }
+    }
+    matches
+}
+
+/// Watched parties named anywhere in `text`.
+pub fn scan_text(entries: &[WatchlistEntryRecord], text: &str) -> Vec<WatchlistMatch> {
+    let haystack = format!(" {} ", normalize_party_name(text));
+    entries
+        .iter()
+        .filter(|entry| !entry.name_normalized.is_empty())
+        .filter(|entry| haystack.contains(&format!(" {} ", entry.name_normalized)))
+        .map(|entry| watchlist_match(entry, None))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn entry(name: &str, category: WatchlistCategory) -> WatchlistEntryRecord {
+        WatchlistEntryRecord {
+            id: Uuid::new_v4(),
+            user_id: "default".to_string(),
+            name: name.to_string(),
+            name_normalized: normalize_party_name(name),
+            category,
+            reason: None,
+            created_by: "default".to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn names_match_after_normalization() {
+        let entries = vec![
+            entry("Globex Corp.", WatchlistCategory::AdverseParty),
+            entry("Rosneft", WatchlistCategory::Sanctioned),
+        ];
+        let matches = screen_names(
+            &entries,
+            &["GLOBEX CORP".to_string(), "Globex Corporation".to_string()],
+        );
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].watched_name, "Globex Corp.");
+        assert_eq!(matches[0].matched_name.as_deref(), Some("GLOBEX CORP"));
+    }
+
+    #[test]
+    fn text_scan_requires_whole_words() {
+        let entries = vec![
+            entry("Rosneft", WatchlistCategory::Sanctioned),
+            entry("Acme", WatchlistCategory::AdverseParty),
+        ];
+        let matches = scan_text(
+            &entries,
+            "Payment routed via ROSNEFT's trading arm; see Acmeville invoice.",
+        );
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].category, WatchlistCategory::Sanctioned);
+        assert!(matches[0].matched_name.is_none());
+    }
+}
@@ -55,6 +55,13 @@ pub fn matter_id_for_path(path: &str, matter_root: &str) -> Option<String> {
     }
 }
 
+/// Leading bytes shared by every serialized matter envelope.
+///
+/// Lets storage queries classify encrypted documents without decoding them.
+pub fn encrypted_payload_prefix() -> String {
+    format!("{{\"format\":\"{MATTER_ENVELOPE_FORMAT}\"")
+}
+
 pub fn is_encrypted_payload(content: &str) -> bool {
     let Ok(envelope) = serde_json::from_str::<MatterEncryptedEnvelope>(content) else {
         return false;
@@ -51,11 +51,14 @@ pub mod db;
 pub mod error;
 pub mod estimation;
 pub mod evaluation;
+pub mod events;
 pub mod extensions;
 pub mod history;
 pub mod hooks;
+pub mod integrations;
 pub mod legal;
 pub mod llm;
+pub mod notifications;
 pub mod observability;
 pub mod orchestrator;
 pub mod pairing;
@@ -0,0 +1,274 @@
+//! Capability-aware provider for self-hosted models (Ollama, llama.cpp,
+//! vLLM, LM Studio).
+//!
+//! Local servers rarely report their limits, and small models usually run
+//! with a context window far below the hosted defaults the agent assumes.
+//! [`LocalModelProvider`] applies the per-deployment
+//! [`LocalModelCapabilities`] around the underlying client:
+//!
+//! - requests that cannot fit the context window fail fast with
+//!   `ContextLengthExceeded`, so the dispatcher compacts and retries instead
+//!   of the server silently truncating the prompt;
+//! - `max_tokens` is clamped to the room left after the prompt;
+//! - tools are withheld from models without native tool calling;
+//! - `model_metadata` reports the configured window and vision support;
+//! - cost is zero.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+
+use crate::config::LocalModelCapabilities;
+use crate::error::LlmError;
+use crate::llm::provider::{
+    ChatMessage, CompletionRequest, CompletionResponse, CompletionStream, LlmProvider,
+    ModelMetadata, ToolCompletionRequest, ToolCompletionResponse, ToolDefinition,
+};
+
+/// Rough characters-per-token ratio for prompt size estimates.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Tokens always left for the reply when clamping `max_tokens`.
+const MIN_OUTPUT_TOKENS: usize = 256;
+
+/// Wraps a local model client with its deployment capabilities.
+pub struct LocalModelProvider {
+    inner: Arc<dyn LlmProvider>,
+    capabilities: LocalModelCapabilities,
+}
+
+impl LocalModelProvider {
+    pub fn new(inner: Arc<dyn LlmProvider>, capabilities: LocalModelCapabilities) -> Self {
+        Self {
+            inner,
+            capabilities,
+        }
+    }
+
+    pub fn capabilities(&self) -> LocalModelCapabilities {
+        self.capabilities
+    }
+
+    /// Check the prompt fits and clamp the requested output to the room left.
+    fn fit(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[ToolDefinition],
+        max_tokens: Option<u32>,
+    ) -> Result<Option<u32>, LlmError> {
+        let limit = self.capabilities.context_length as usize;
+        let used = estimate_prompt_tokens(messages, tools);
+        if used + MIN_OUTPUT_TOKENS > limit {
+            return Err(LlmError::ContextLengthExceeded { used, limit });
+        }
+        let room = (limit - used) as u32;
+        Ok(Some(
+            max_tokens.map_or(room, |requested| requested.min(room)),
+        ))
+    }
+
+    fn without_tools(request: ToolCompletionRequest) -> CompletionRequest {
+        let mut simple = CompletionRequest::new(request.messages);
+        simple.model = request.model;
+        simple.max_tokens = request.max_tokens;
+        simple.temperature = request.temperature;
+        simple.metadata = request.metadata;
+        simple
+    }
+}
+
+/// Approximate prompt size in tokens, including tool schemas.
+fn estimate_prompt_tokens(messages: &[ChatMessage], tools: &[ToolDefinition]) -> usize {
+    let message_chars: usize = messages
+        .iter()
+        .map(|m| {
+            m.content.len()
+                + m.tool_calls.as_ref().map_or(0, |calls| {
+                    calls
+                        .iter()
+                        .map(|c| c.name.len() + c.arguments.to_string().len())
+                        .sum()
+                })
+        })
+        .sum();
+    let tool_chars: usize = tools
+        .iter()
+        .map(|t| t.name.len() + t.description.len() + t.parameters.to_string().len())
+        .sum();
+    (message_chars + tool_chars).div_ceil(CHARS_PER_TOKEN)
+}
+
+#[async_trait]
+impl LlmProvider for LocalModelProvider {
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn cost_per_token(&self) -> (Decimal, Decimal) {
+        (Decimal::ZERO, Decimal::ZERO)
+    }
+
+    async fn complete(
+        &self,
+        mut request: CompletionRequest,
+    ) -> Result<CompletionResponse, LlmError> {
+        request.max_tokens = self.fit(&request.messages, &[], request.max_tokens)?;
+        self.inner.complete(request).await
+    }
+
+    async fn complete_with_tools(
+        &self,
+        mut request: ToolCompletionRequest,
+    ) -> Result<ToolCompletionResponse, LlmError> {
+        if !self.capabilities.tool_calling {
+            let response = self.complete(Self::without_tools(request)).await?;
+            return Ok(ToolCompletionResponse {
+                content: Some(response.content),
+                tool_calls: Vec::new(),
+                input_tokens: response.input_tokens,
+                output_tokens: response.output_tokens,
+                finish_reason: response.finish_reason,
+            });
+        }
+        request.max_tokens = self.fit(&request.messages, &request.tools, request.max_tokens)?;
+        self.inner.complete_with_tools(request).await
+    }
+
+    async fn complete_stream(
+        &self,
+        mut request: ToolCompletionRequest,
+    ) -> Result<CompletionStream, LlmError> {
+        if !self.capabilities.tool_calling {
+            request.tools.clear();
+            request.tool_choice = None;
+        }
+        request.max_tokens = self.fit(&request.messages, &request.tools, request.max_tokens)?;
+        self.inner.complete_stream(request).await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    async fn list_models(&self) -> Result<Vec<String>, LlmError> {
+        self.inner.list_models().await
+    }
+
+    async fn model_metadata(&self) -> Result<ModelMetadata, LlmError> {
+        Ok(ModelMetadata {
+            id: self.inner.active_model_name(),
+            context_length: Some(self.capabilities.context_length),
+            supports_vision: Some(self.capabilities.vision),
+        })
+    }
+
+    fn effective_model_name(&self, requested_model: Option<&str>) -> String {
+        self.inner.effective_model_name(requested_model)
+    }
+
+    fn active_model_name(&self) -> String {
+        self.inner.active_model_name()
+    }
+
+    fn set_model(&self, model: &str) -> Result<(), LlmError> {
+        self.inner.set_model(model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::StubLlm;
+
+    fn small_model() -> LocalModelCapabilities {
+        LocalModelCapabilities {
+            context_length: 1024,
+            vision: false,
+            tool_calling: true,
+        }
+    }
+
+    fn tool(name: &str) -> ToolDefinition {
+        ToolDefinition {
+            name: name.to_string(),
+            description: "Search matter documents".to_string(),
+            parameters: serde_json::json!({ "type": "object" }),
+        }
+    }
+
+    #[tokio::test]
+    async fn oversized_prompts_fail_with_context_length_exceeded() {
+        let stub = Arc::new(StubLlm::new("ok"));
+        let provider = LocalModelProvider::new(stub.clone(), small_model());
+
+        let long = "word ".repeat(1024);
+        let err = provider
+            .complete(CompletionRequest::new(vec![ChatMessage::user(long)]))
+            .await
+            .expect_err("prompt exceeds the window");
+        assert!(matches!(
+            err,
+            LlmError::ContextLengthExceeded { limit: 1024, .. }
+        ));
+        assert_eq!(stub.calls(), 0);
+
+        let response = provider
+            .complete(CompletionRequest::new(vec![ChatMessage::user("hello")]))
+            .await
+            .expect("small prompt fits");
+        assert_eq!(response.content, "ok");
+        assert_eq!(stub.calls(), 1);
+    }
+
+    #[test]
+    fn max_tokens_is_clamped_to_remaining_window() {
+        let provider = LocalModelProvider::new(Arc::new(StubLlm::new("ok")), small_model());
+        let messages = vec![ChatMessage::user("a".repeat(400))];
+
+        assert_eq!(
+            provider.fit(&messages, &[], Some(4096)).expect("fits"),
+            Some(924)
+        );
+        assert_eq!(
+            provider.fit(&messages, &[], Some(100)).expect("fits"),
+            Some(100)
+        );
+        assert!(provider.fit(&messages, &[tool("search")], None).unwrap() < Some(924));
+    }
+
+    #[tokio::test]
+    async fn tools_are_withheld_without_native_tool_calling() {
+        let stub = Arc::new(StubLlm::new("plain answer"));
+        let provider = LocalModelProvider::new(
+            stub.clone(),
+            LocalModelCapabilities {
+                tool_calling: false,
+                ..small_model()
+            },
+        );
+
+        let response = provider
+            .complete_with_tools(ToolCompletionRequest::new(
+                vec![ChatMessage::user("find the lease")],
+                vec![tool("memory_search")],
+            ))
+            .await
+            .expect("falls back to plain completion");
+        assert_eq!(response.content.as_deref(), Some("plain answer"));
+        assert!(response.tool_calls.is_empty());
+    }
+
+    #[tokio::test]
+    async fn metadata_reports_configured_capabilities_and_zero_cost() {
+        let provider = LocalModelProvider::new(
+            Arc::new(StubLlm::new("ok").with_model_name("llama3.1:8b")),
+            small_model(),
+        );
+        let meta = provider.model_metadata().await.expect("metadata");
+        assert_eq!(meta.id, "llama3.1:8b");
+        assert_eq!(meta.context_length, Some(1024));
+        assert_eq!(meta.supports_vision, Some(false));
+        assert_eq!(provider.calculate_cost(1000, 1000), Decimal::ZERO);
+    }
+}
@@ -6,10 +6,16 @@
 //! - **Anthropic**: Direct API access with your own key
 //! - **Ollama**: Local model inference
 //! - **OpenAI-compatible**: Any endpoint that speaks the OpenAI API
+//!
+//! Ollama and OpenAI-compatible servers on local addresses are wrapped in
+//! [`LocalModelProvider`], which applies the deployment's context window and
+//! tool-calling capabilities. `LLM_AIR_GAPPED=true` restricts the agent to
+//! those backends.
 
 pub mod circuit_breaker;
 pub mod costs;
 pub mod failover;
+pub mod local;
 mod nearai_chat;
 mod provider;
 mod reasoning;
@@ -18,30 +24,34 @@ pub mod retry;
 mod rig_adapter;
 pub mod session;
 pub mod smart_routing;
+pub mod task_routing;
 
 pub use circuit_breaker::{CircuitBreakerConfig, CircuitBreakerProvider};
 pub use failover::{CooldownConfig, FailoverProvider};
+pub use local::LocalModelProvider;
 pub use nearai_chat::{ModelInfo, NearAiChatProvider};
 pub use provider::{
-    ChatMessage, CompletionRequest, CompletionResponse, FinishReason, LlmProvider, ModelMetadata,
-    Role, ToolCall, ToolCompletionRequest, ToolCompletionResponse, ToolDefinition, ToolResult,
+    ChatMessage, CompletionRequest, CompletionResponse, CompletionStream, FinishReason,
+    LlmProvider, ModelMetadata, Role, StreamChunk, ToolCall, ToolCompletionRequest,
+    ToolCompletionResponse, ToolDefinition, ToolResult, response_to_stream,
 };
 pub use reasoning::{
     ActionPlan, Reasoning, ReasoningContext, RespondOutput, RespondResult, SILENT_REPLY_TOKEN,
     TokenUsage, ToolSelection, is_silent_reply,
 };
-pub use response_cache::{CachedProvider, ResponseCacheConfig};
+pub use response_cache::{CacheControl, CachedProvider, ResponseCacheConfig};
 pub use retry::{RetryConfig, RetryProvider};
 pub use rig_adapter::RigAdapter;
 pub use session::{SessionConfig, SessionManager, create_session_manager};
 pub use smart_routing::{SmartRoutingConfig, SmartRoutingProvider, TaskComplexity};
+pub use task_routing::{LlmTask, TaskRoute, TaskRoutingProvider};
 
 use std::sync::Arc;
 
 use rig::client::CompletionClient;
 use secrecy::ExposeSecret;
 
-use crate::config::{LlmBackend, LlmConfig, NearAiConfig};
+use crate::config::{LegalLlmRoutingConfig, LlmBackend, LlmConfig, LlmRouteTarget, NearAiConfig};
 use crate::error::LlmError;
 
 /// Create an LLM provider based on configuration.
@@ -147,10 +157,16 @@ fn create_anthropic_provider(config: &LlmConfig) -> Result<Arc<dyn LlmProvider>,
     })?;
 
     let model = client.completion_model(&anth.model);
+    let model = if anth.prompt_caching {
+        model.with_prompt_caching()
+    } else {
+        model
+    };
     tracing::info!(
-        "Using Anthropic direct API (model: {}, base_url: {})",
+        "Using Anthropic direct API (model: {}, base_url: {}, prompt caching: {})",
         anth.model,
         anth.base_url.as_deref().unwrap_or("default"),
+        anth.prompt_caching,
     );
     Ok(Arc::new(RigAdapter::new(model, &anth.model)))
 }
@@ -174,11 +190,15 @@ fn create_ollama_provider(config: &LlmConfig) -> Result<Arc<dyn LlmProvider>, Ll
 
     let model = client.completion_model(&oll.model);
     tracing::info!(
-        "Using Ollama (base_url: {}, model: {})",
+        "Using Ollama (base_url: {}, model: {}, context: {} tokens)",
         oll.base_url,
-        oll.model
+        oll.model,
+        oll.capabilities.context_length
     );
-    Ok(Arc::new(RigAdapter::new(model, &oll.model)))
+    Ok(Arc::new(LocalModelProvider::new(
+        Arc::new(RigAdapter::new(model, &oll.model)),
+        oll.capabilities,
+    )))
 }
 
 const TINFOIL_BASE_URL: &str = "https://inference.tinfoil.sh/v1";
@@ -262,7 +282,54 @@ fn create_openai_compatible_provider(config: &LlmConfig) -> Result<Arc<dyn LlmPr
         compat.base_url,
         compat.model
     );
-    Ok(Arc::new(RigAdapter::new(model, &compat.model)))
+    let adapter: Arc<dyn LlmProvider> = Arc::new(RigAdapter::new(model, &compat.model));
+    Ok(match compat.local_capabilities {
+        Some(capabilities) => Arc::new(LocalModelProvider::new(adapter, capabilities)),
+        None => adapter,
+    })
+}
+
+/// Create a provider for one `backend:model` route target.
+///
+/// Reuses the credentials already resolved for that backend; fails with
+/// `AuthFailed` when the backend has no configuration.
+pub fn create_llm_provider_for_target(
+    config: &LlmConfig,
+    target: &LlmRouteTarget,
+    session: Arc<SessionManager>,
+) -> Result<Arc<dyn LlmProvider>, LlmError> {
+    let mut target_config = config.clone();
+    target_config.backend = target.backend;
+    let model = target.model.clone();
+    match target.backend {
+        LlmBackend::NearAi => target_config.nearai.model = model,
+        LlmBackend::OpenAi => {
+            if let Some(openai) = target_config.openai.as_mut() {
+                openai.model = model;
+            }
+        }
+        LlmBackend::Anthropic => {
+            if let Some(anthropic) = target_config.anthropic.as_mut() {
+                anthropic.model = model;
+            }
+        }
+        LlmBackend::Ollama => {
+            if let Some(ollama) = target_config.ollama.as_mut() {
+                ollama.model = model;
+            }
+        }
+        LlmBackend::OpenAiCompatible => {
+            if let Some(compatible) = target_config.openai_compatible.as_mut() {
+                compatible.model = model;
+            }
+        }
+        LlmBackend::Tinfoil => {
+            if let Some(tinfoil) = target_config.tinfoil.as_mut() {
+                tinfoil.model = model;
+            }
+        }
+    }
+    create_llm_provider(&target_config, session)
 }
 
 /// Create a cheap/fast LLM provider for lightweight tasks (heartbeat, routing, evaluation).
@@ -303,7 +370,7 @@ pub fn create_cheap_llm_provider(
 /// 3. SmartRoutingProvider (cheap/primary split when cheap model is configured)
 /// 4. FailoverProvider (fallback model when primary fails)
 /// 5. CircuitBreakerProvider (fast-fail when backend is degraded)
-/// 6. CachedProvider (in-memory response cache)
+/// 6. CachedProvider (response cache, persisted in `cache_store` when given)
 ///
 /// Also returns a separate cheap LLM provider for heartbeat/evaluation (not
 /// part of the chain — it's a standalone provider for explicitly cheap tasks).
@@ -314,6 +381,7 @@ pub fn create_cheap_llm_provider(
 pub fn build_provider_chain(
     config: &LlmConfig,
     session: Arc<SessionManager>,
+    cache_store: Option<Arc<dyn crate::db::LlmResponseCacheStore>>,
 ) -> Result<(Arc<dyn LlmProvider>, Option<Arc<dyn LlmProvider>>), LlmError> {
     let llm = create_llm_provider(config, session.clone())?;
     tracing::info!("LLM provider initialized: {}", llm.model_name());
@@ -417,12 +485,18 @@ pub fn build_provider_chain(
             ttl: std::time::Duration::from_secs(config.nearai.response_cache_ttl_secs),
             max_entries: config.nearai.response_cache_max_entries,
         };
+        let cache_store = cache_store.filter(|_| config.nearai.response_cache_persistent);
         tracing::info!(
             ttl_secs = config.nearai.response_cache_ttl_secs,
             max_entries = config.nearai.response_cache_max_entries,
+            persistent = cache_store.is_some(),
             "LLM response cache enabled"
         );
-        Arc::new(CachedProvider::new(llm, rc_config))
+        let cached = CachedProvider::new(llm, rc_config);
+        Arc::new(match cache_store {
+            Some(store) => cached.with_store(store),
+            None => cached,
+        })
     } else {
         llm
     };
@@ -436,6 +510,67 @@ pub fn build_provider_chain(
     Ok((llm, cheap_llm))
 }
 
+/// Wrap `llm` in a [`TaskRoutingProvider`] built from the legal LLM routes.
+///
+/// Each route gets retry-wrapped providers for its models with failover
+/// between them in the configured order; requests that match no route keep
+/// using `llm`. Returns `llm` unchanged when no routes are configured.
+pub fn apply_task_routing(
+    llm: Arc<dyn LlmProvider>,
+    config: &LlmConfig,
+    routing: &LegalLlmRoutingConfig,
+    session: Arc<SessionManager>,
+) -> Result<Arc<dyn LlmProvider>, LlmError> {
+    if routing.routes.is_empty() {
+        return Ok(llm);
+    }
+    let retry_config = RetryConfig {
+        max_retries: config.nearai.max_retries,
+    };
+    let cooldown_config = CooldownConfig {
+        cooldown_duration: std::time::Duration::from_secs(config.nearai.failover_cooldown_secs),
+        failure_threshold: config.nearai.failover_cooldown_threshold,
+    };
+    let mut routes = Vec::with_capacity(routing.routes.len());
+    for route in &routing.routes {
+        let mut providers = Vec::with_capacity(route.models.len());
+        for target in &route.models {
+            let provider = create_llm_provider_for_target(config, target, session.clone())?;
+            let provider: Arc<dyn LlmProvider> = if retry_config.max_retries > 0 {
+                Arc::new(RetryProvider::new(provider, retry_config.clone()))
+            } else {
+                provider
+            };
+            providers.push(provider);
+        }
+        let provider: Arc<dyn LlmProvider> = if providers.len() == 1 {
+            providers.remove(0)
+        } else {
+            Arc::new(FailoverProvider::with_cooldown(
+                providers,
+                cooldown_config.clone(),
+            )?)
+        };
+        tracing::info!(
+            task = route.task.map(|t| t.as_str()).unwrap_or("*"),
+            confidentiality = route.confidentiality.as_deref().unwrap_or("*"),
+            models = %route
+                .models
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+            "LLM task route enabled"
+        );
+        routes.push(TaskRoute::new(
+            route.task,
+            route.confidentiality.clone(),
+            provider,
+        ));
+    }
+    Ok(Arc::new(TaskRoutingProvider::new(llm, routes)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -455,6 +590,7 @@ mod tests {
             circuit_breaker_threshold: None,
             circuit_breaker_recovery_secs: 30,
             response_cache_enabled: false,
+            response_cache_persistent: true,
             response_cache_ttl_secs: 3600,
             response_cache_max_entries: 1000,
             failover_cooldown_secs: 300,
@@ -472,6 +608,7 @@ mod tests {
             ollama: None,
             openai_compatible: None,
             tinfoil: None,
+            air_gapped: false,
         }
     }
 
@@ -499,6 +636,34 @@ mod tests {
         assert_eq!(provider.unwrap().model_name(), "cheap-test-model");
     }
 
+    #[test]
+    fn task_routing_requires_configured_backends() {
+        let config = test_llm_config();
+        let session = Arc::new(SessionManager::new(SessionConfig::default()));
+        let default = create_llm_provider(&config, session.clone()).expect("default provider");
+
+        let untouched = apply_task_routing(
+            Arc::clone(&default),
+            &config,
+            &LegalLlmRoutingConfig::default(),
+            session.clone(),
+        )
+        .expect("no routes");
+        assert!(Arc::ptr_eq(&untouched, &default));
+
+        let routing = LegalLlmRoutingConfig {
+            routes: vec![crate::config::LegalLlmRoute {
+                task: Some(LlmTask::Drafting),
+                confidentiality: None,
+                models: vec!["openai:gpt-4o".parse().expect("target")],
+            }],
+        };
+        let err = apply_task_routing(default, &config, &routing, session)
+            .err()
+            .expect("openai is not configured");
+        assert!(matches!(err, LlmError::AuthFailed { .. }));
+    }
+
     #[test]
     fn test_create_cheap_llm_provider_ignored_for_non_nearai_backend() {
         let mut config = test_llm_config();
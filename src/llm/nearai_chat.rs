@@ -960,6 +960,7 @@ mod tests {
             circuit_breaker_threshold: None,
             circuit_breaker_recovery_secs: 30,
             response_cache_enabled: false,
+            response_cache_persistent: true,
             response_cache_ttl_secs: 3600,
             response_cache_max_entries: 1000,
             failover_cooldown_secs: 300,
@@ -1,6 +1,9 @@
 //! LLM provider trait and types.
 
+use std::pin::Pin;
+
 use async_trait::async_trait;
+use futures::Stream;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
@@ -144,10 +147,28 @@ impl CompletionRequest {
         self.temperature = Some(temperature);
         self
     }
+
+    /// Tag the request with a task type for per-task model routing.
+    pub fn with_task(mut self, task: crate::llm::LlmTask) -> Self {
+        self.metadata.insert(
+            crate::llm::task_routing::TASK_METADATA_KEY.to_string(),
+            task.as_str().to_string(),
+        );
+        self
+    }
+
+    /// Set how the response cache treats this request.
+    pub fn with_cache_control(mut self, control: crate::llm::CacheControl) -> Self {
+        self.metadata.insert(
+            crate::llm::response_cache::CACHE_CONTROL_METADATA_KEY.to_string(),
+            control.to_metadata(),
+        );
+        self
+    }
 }
 
 /// Response from a chat completion.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompletionResponse {
     pub content: String,
     pub input_tokens: u32,
@@ -156,7 +177,8 @@ pub struct CompletionResponse {
 }
 
 /// Why the completion finished.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum FinishReason {
     Stop,
     Length,
@@ -242,10 +264,22 @@ impl ToolCompletionRequest {
         self.tool_choice = Some(choice.into());
         self
     }
+
+    /// Set how the response cache treats this request. Tool completions are
+    /// only cached when the call site opts in with [`CacheControl::Cache`].
+    ///
+    /// [`CacheControl::Cache`]: crate::llm::CacheControl::Cache
+    pub fn with_cache_control(mut self, control: crate::llm::CacheControl) -> Self {
+        self.metadata.insert(
+            crate::llm::response_cache::CACHE_CONTROL_METADATA_KEY.to_string(),
+            control.to_metadata(),
+        );
+        self
+    }
 }
 
 /// Response from a completion with potential tool calls.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolCompletionResponse {
     /// Text content (may be empty if tool calls are present).
     pub content: Option<String>,
@@ -262,6 +296,66 @@ pub struct ModelMetadata {
     pub id: String,
     /// Total context window size in tokens.
     pub context_length: Option<u32>,
+    /// Whether the model accepts image input (`None` when unknown).
+    pub supports_vision: Option<bool>,
+}
+
+/// One incremental event of a streamed completion.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamChunk {
+    /// A text delta.
+    Text(String),
+    /// A tool call fragment. `id` and `name` arrive on the first fragment for
+    /// an `index`; `arguments` fragments concatenate into the JSON arguments.
+    ToolCall {
+        index: u32,
+        id: Option<String>,
+        name: Option<String>,
+        arguments: Option<String>,
+    },
+    /// Terminal event with usage and finish reason.
+    Done {
+        input_tokens: u32,
+        output_tokens: u32,
+        finish_reason: FinishReason,
+    },
+}
+
+/// Stream of completion chunks returned by [`LlmProvider::complete_stream`].
+pub type CompletionStream = Pin<Box<dyn Stream<Item = Result<StreamChunk, LlmError>> + Send>>;
+
+/// Replay a complete response as stream chunks.
+///
+/// Text is split on word boundaries into ~20 byte deltas so clients still
+/// render progressively; each tool call becomes a single fragment.
+pub fn response_to_stream(response: ToolCompletionResponse) -> CompletionStream {
+    let mut chunks = Vec::new();
+    if let Some(content) = response.content.as_deref() {
+        let mut buf = String::new();
+        for word in content.split_inclusive(char::is_whitespace) {
+            buf.push_str(word);
+            if buf.len() >= 20 {
+                chunks.push(Ok(StreamChunk::Text(std::mem::take(&mut buf))));
+            }
+        }
+        if !buf.is_empty() {
+            chunks.push(Ok(StreamChunk::Text(buf)));
+        }
+    }
+    for (index, call) in response.tool_calls.iter().enumerate() {
+        chunks.push(Ok(StreamChunk::ToolCall {
+            index: index as u32,
+            id: Some(call.id.clone()),
+            name: Some(call.name.clone()),
+            arguments: Some(serde_json::to_string(&call.arguments).unwrap_or_default()),
+        }));
+    }
+    chunks.push(Ok(StreamChunk::Done {
+        input_tokens: response.input_tokens,
+        output_tokens: response.output_tokens,
+        finish_reason: response.finish_reason,
+    }));
+    Box::pin(futures::stream::iter(chunks))
 }
 
 /// Trait for LLM providers.
@@ -282,6 +376,41 @@ pub trait LlmProvider: Send + Sync {
         request: ToolCompletionRequest,
     ) -> Result<ToolCompletionResponse, LlmError>;
 
+    /// Stream a completion as incremental chunks.
+    ///
+    /// Default implementation runs the full completion (`complete` when no
+    /// tools are offered, `complete_with_tools` otherwise) and replays it via
+    /// [`response_to_stream`]. Errors before the first chunk are returned
+    /// directly so callers can still map them to HTTP errors.
+    async fn complete_stream(
+        &self,
+        request: ToolCompletionRequest,
+    ) -> Result<CompletionStream, LlmError> {
+        let response = if request.tools.is_empty() {
+            let mut simple = CompletionRequest::new(request.messages);
+            simple.model = request.model;
+            simple.max_tokens = request.max_tokens;
+            simple.temperature = request.temperature;
+            let response = self.complete(simple).await?;
+            ToolCompletionResponse {
+                content: Some(response.content),
+                tool_calls: Vec::new(),
+                input_tokens: response.input_tokens,
+                output_tokens: response.output_tokens,
+                finish_reason: response.finish_reason,
+            }
+        } else {
+            self.complete_with_tools(request).await?
+        };
+        Ok(response_to_stream(response))
+    }
+
+    /// Whether `complete_stream` yields tokens as they are generated rather
+    /// than replaying a finished response. Default: `false`.
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
     /// List available models from the provider.
     /// Default implementation returns empty list.
     async fn list_models(&self) -> Result<Vec<String>, LlmError> {
@@ -294,6 +423,7 @@ pub trait LlmProvider: Send + Sync {
         Ok(ModelMetadata {
             id: self.model_name().to_string(),
             context_length: None,
+            supports_vision: None,
         })
     }
 
@@ -384,8 +514,59 @@ pub fn sanitize_tool_messages(messages: &mut [ChatMessage]) {
 
 #[cfg(test)]
 mod tests {
+    use futures::StreamExt;
+
     use super::*;
 
+    #[tokio::test]
+    async fn test_response_to_stream_replays_text_and_tool_calls() {
+        let response = ToolCompletionResponse {
+            content: Some("The hearing is set for the fourteenth of May.".to_string()),
+            tool_calls: vec![ToolCall {
+                id: "call_1".to_string(),
+                name: "calendar".to_string(),
+                arguments: serde_json::json!({"date": "2026-05-14"}),
+            }],
+            input_tokens: 3,
+            output_tokens: 4,
+            finish_reason: FinishReason::ToolUse,
+        };
+        let chunks: Vec<StreamChunk> = response_to_stream(response)
+            .map(|chunk| chunk.expect("replayed chunks never fail"))
+            .collect()
+            .await;
+
+        let text: String = chunks
+            .iter()
+            .filter_map(|chunk| match chunk {
+                StreamChunk::Text(t) => Some(t.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(text, "The hearing is set for the fourteenth of May.");
+        assert!(
+            chunks
+                .iter()
+                .filter(|c| matches!(c, StreamChunk::Text(_)))
+                .count()
+                > 1
+        );
+        assert!(chunks.contains(&StreamChunk::ToolCall {
+            index: 0,
+            id: Some("call_1".to_string()),
+            name: Some("calendar".to_string()),
+            arguments: Some(r#"{"date":"2026-05-14"}"#.to_string()),
+        }));
+        assert_eq!(
+            chunks.last(),
+            Some(&StreamChunk::Done {
+                input_tokens: 3,
+                output_tokens: 4,
+                finish_reason: FinishReason::ToolUse,
+            })
+        );
+    }
+
     #[test]
     fn test_sanitize_preserves_valid_pairs() {
         let tc = ToolCall {
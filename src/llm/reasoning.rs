@@ -328,6 +328,18 @@ impl Reasoning {
         self
     }
 
+    /// Tag outgoing request metadata with the active matter's
+    /// confidentiality level so task routing can keep it on approved models.
+    fn tag_confidentiality(&self, metadata: &mut std::collections::HashMap<String, String>) {
+        if let Some(ctx) = self.active_matter_context.as_ref()
+            && !ctx.confidentiality.trim().is_empty()
+        {
+            metadata
+                .entry(crate::llm::task_routing::CONFIDENTIALITY_METADATA_KEY.to_string())
+                .or_insert_with(|| ctx.confidentiality.trim().to_string());
+        }
+    }
+
     /// Run a simple LLM completion with automatic response cleaning.
     ///
     /// This is the preferred entry point for code paths that call the LLM
@@ -338,6 +350,8 @@ impl Reasoning {
         &self,
         request: CompletionRequest,
     ) -> Result<(String, TokenUsage), LlmError> {
+        let mut request = request;
+        self.tag_confidentiality(&mut request.metadata);
         let response = self.llm.complete(request).await?;
         let usage = TokenUsage {
             input_tokens: response.input_tokens,
@@ -360,9 +374,10 @@ impl Reasoning {
             )));
         }
 
-        let request = CompletionRequest::new(messages)
+        let mut request = CompletionRequest::new(messages)
             .with_max_tokens(2048)
             .with_temperature(0.3);
+        self.tag_confidentiality(&mut request.metadata);
 
         let response = self.llm.complete(request).await?;
 
@@ -396,6 +411,7 @@ impl Reasoning {
                 .with_max_tokens(1024)
                 .with_tool_choice("auto");
         request.metadata = context.metadata.clone();
+        self.tag_confidentiality(&mut request.metadata);
 
         let response = self.llm.complete_with_tools(request).await?;
 
@@ -454,9 +470,10 @@ Respond in JSON format:
             )));
         }
 
-        let request = CompletionRequest::new(messages)
+        let mut request = CompletionRequest::new(messages)
             .with_max_tokens(1024)
             .with_temperature(0.1);
+        self.tag_confidentiality(&mut request.metadata);
 
         let response = self.llm.complete(request).await?;
 
@@ -512,6 +529,7 @@ Respond in JSON format:
                 .with_temperature(0.7)
                 .with_tool_choice("auto");
             request.metadata = context.metadata.clone();
+            self.tag_confidentiality(&mut request.metadata);
 
             let response = self.llm.complete_with_tools(request).await?;
             let usage = TokenUsage {
@@ -578,6 +596,7 @@ Respond in JSON format:
                 .with_max_tokens(4096)
                 .with_temperature(0.7);
             request.metadata = context.metadata.clone();
+            self.tag_confidentiality(&mut request.metadata);
 
             let response = self.llm.complete(request).await?;
             let cleaned = clean_response(&response.content);
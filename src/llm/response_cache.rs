@@ -1,19 +1,31 @@
-//! In-memory LLM response cache with TTL and LRU eviction.
+//! LLM response cache with TTL and LRU eviction, optionally persisted.
 //!
-//! Wraps any [`LlmProvider`] and caches [`complete()`] responses keyed
-//! by a SHA-256 hash of the messages and model name. Tool-calling
-//! requests are never cached since they can trigger side effects.
+//! Wraps any [`LlmProvider`] and caches responses keyed by a SHA-256 hash
+//! of the normalized model, system prompt, messages, and tool schema (see
+//! [`cache_key`]). Entries live in memory and, when a
+//! [`LlmResponseCacheStore`] is attached, in the database so identical
+//! contexts are reused across restarts and repeated routine runs.
+//!
+//! Call sites steer the cache with [`CacheControl`]. Without a directive,
+//! `complete()` is cached with the configured TTL and tool completions are
+//! forwarded uncached, since they can drive side effects; a call site that
+//! knows its tool loop is safe to replay opts in with
+//! [`CacheControl::Cache`]. Streams are never cached.
 //!
 //! ```text
-//! ┌──────────────────────────────────────────────────┐
-//! │               CachedProvider                      │
-//! │  complete() ──► cache lookup ──► hit? return      │
-//! │                                  miss? call inner │
-//! │                                  store response   │
-//! │                                                    │
-//! │  complete_with_tools() ──► always call inner       │
-//! └──────────────────────────────────────────────────┘
+//! ┌───────────────────────────────────────────────────────┐
+//! │                    CachedProvider                      │
+//! │  complete() ──► memory ──► database ──► hit? return    │
+//! │                                         miss? call     │
+//! │                                         inner, store   │
+//! │                                                         │
+//! │  complete_with_tools() ──► same path when opted in,    │
+//! │                            otherwise call inner         │
+//! └───────────────────────────────────────────────────────┘
 //! ```
+//!
+//! Cache hits report zero token usage so cost tracking does not charge for
+//! them.
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -21,15 +33,63 @@ use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use rust_decimal::Decimal;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use sha2::{Digest, Sha256};
 use tokio::sync::Mutex;
 
+use crate::db::LlmResponseCacheStore;
 use crate::error::LlmError;
 use crate::llm::provider::{
-    CompletionRequest, CompletionResponse, LlmProvider, ModelMetadata, ToolCompletionRequest,
-    ToolCompletionResponse,
+    ChatMessage, CompletionRequest, CompletionResponse, CompletionStream, LlmProvider,
+    ModelMetadata, Role, ToolCompletionRequest, ToolCompletionResponse, ToolDefinition,
 };
 
+/// Request metadata key carrying a [`CacheControl`] directive.
+pub const CACHE_CONTROL_METADATA_KEY: &str = "cache_control";
+
+/// Per-call-site cache directive, set with `with_cache_control` on a
+/// request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheControl {
+    /// Read and write the cache, tool completions included. `ttl`
+    /// overrides the configured TTL.
+    Cache { ttl: Option<Duration> },
+    /// Skip the lookup but store the fresh response.
+    Refresh,
+    /// Never read or write the cache.
+    Bypass,
+}
+
+impl CacheControl {
+    /// Metadata encoding: `cache`, `cache:<secs>`, `refresh`, or `bypass`.
+    pub fn to_metadata(self) -> String {
+        match self {
+            Self::Cache { ttl: None } => "cache".to_string(),
+            Self::Cache { ttl: Some(ttl) } => format!("cache:{}", ttl.as_secs()),
+            Self::Refresh => "refresh".to_string(),
+            Self::Bypass => "bypass".to_string(),
+        }
+    }
+
+    /// Directive carried in request metadata, if any. Unrecognized values
+    /// are ignored.
+    pub fn from_metadata(metadata: &HashMap<String, String>) -> Option<Self> {
+        let raw = metadata.get(CACHE_CONTROL_METADATA_KEY)?.trim();
+        match raw {
+            "cache" => Some(Self::Cache { ttl: None }),
+            "refresh" => Some(Self::Refresh),
+            "bypass" => Some(Self::Bypass),
+            _ => raw
+                .strip_prefix("cache:")
+                .and_then(|secs| secs.parse::<u64>().ok())
+                .map(|secs| Self::Cache {
+                    ttl: Some(Duration::from_secs(secs)),
+                }),
+        }
+    }
+}
+
 /// Configuration for the response cache.
 #[derive(Debug, Clone)]
 pub struct ResponseCacheConfig {
@@ -49,19 +109,19 @@ impl Default for ResponseCacheConfig {
 }
 
 struct CacheEntry {
-    response: CompletionResponse,
-    created_at: Instant,
+    /// Serialized `CompletionResponse` or `ToolCompletionResponse`.
+    response: serde_json::Value,
+    expires_at: Instant,
     last_accessed: Instant,
     hit_count: u64,
 }
 
-/// LLM provider wrapper that caches `complete()` responses.
-///
-/// Tool completion requests are always forwarded without caching since
-/// tool calls can have side effects that should not be replayed.
+/// LLM provider wrapper that caches responses in memory and, optionally,
+/// in the database.
 pub struct CachedProvider {
     inner: Arc<dyn LlmProvider>,
     cache: Mutex<HashMap<String, CacheEntry>>,
+    store: Option<Arc<dyn LlmResponseCacheStore>>,
     config: ResponseCacheConfig,
 }
 
@@ -71,10 +131,17 @@ impl CachedProvider {
         Self {
             inner,
             cache: Mutex::new(HashMap::new()),
+            store: None,
             config,
         }
     }
 
+    /// Persist entries in `store` as a second tier behind the memory cache.
+    pub fn with_store(mut self, store: Arc<dyn LlmResponseCacheStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
     /// Number of entries currently in the cache.
     pub async fn len(&self) -> usize {
         self.cache.lock().await.len()
@@ -94,45 +161,276 @@ impl CachedProvider {
     pub async fn clear(&self) {
         self.cache.lock().await.clear();
     }
+
+    /// Look `key` up in memory, then in the database.
+    async fn lookup(&self, key: &str) -> Option<serde_json::Value> {
+        let now = Instant::now();
+        {
+            let mut guard = self.cache.lock().await;
+            if let Some(entry) = guard.get_mut(key) {
+                if now < entry.expires_at {
+                    entry.last_accessed = now;
+                    entry.hit_count += 1;
+                    tracing::debug!(hits = entry.hit_count, "response cache hit");
+                    return Some(entry.response.clone());
+                }
+                // Expired, remove it
+                guard.remove(key);
+            }
+        }
+
+        let store = self.store.as_ref()?;
+        let entry = match store.get_llm_cache_entry(key).await {
+            Ok(entry) => entry?,
+            Err(err) => {
+                tracing::warn!("Response cache lookup failed: {}", err);
+                return None;
+            }
+        };
+        tracing::debug!(hits = entry.hit_count, "persistent response cache hit");
+        let remaining = (entry.expires_at - chrono::Utc::now())
+            .to_std()
+            .unwrap_or_default();
+        let mut guard = self.cache.lock().await;
+        self.insert_locked(&mut guard, key, entry.response.clone(), now, remaining);
+        if let Some(memory) = guard.get_mut(key) {
+            memory.hit_count = 1;
+        }
+        Some(entry.response)
+    }
+
+    /// Store a fresh response in memory and, when attached, the database.
+    async fn remember(&self, key: String, model: &str, response: serde_json::Value, ttl: Duration) {
+        if let Some(store) = self.store.as_ref() {
+            let expires_at = chrono::Utc::now()
+                + chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::MAX);
+            if let Err(err) = store
+                .put_llm_cache_entry(&key, model, &response, expires_at)
+                .await
+            {
+                tracing::warn!("Response cache write failed: {}", err);
+            }
+        }
+        let mut guard = self.cache.lock().await;
+        self.insert_locked(&mut guard, &key, response, Instant::now(), ttl);
+    }
+
+    fn insert_locked(
+        &self,
+        guard: &mut HashMap<String, CacheEntry>,
+        key: &str,
+        response: serde_json::Value,
+        now: Instant,
+        ttl: Duration,
+    ) {
+        // Evict expired entries
+        guard.retain(|_, entry| now < entry.expires_at);
+
+        // LRU eviction if over capacity
+        while guard.len() >= self.config.max_entries {
+            let oldest_key = guard
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_accessed)
+                .map(|(k, _)| k.clone());
+
+            if let Some(k) = oldest_key {
+                guard.remove(&k);
+            } else {
+                break;
+            }
+        }
+
+        guard.insert(
+            key.to_string(),
+            CacheEntry {
+                response,
+                expires_at: now + ttl,
+                last_accessed: now,
+                hit_count: 0,
+            },
+        );
+    }
+
+    /// Serve `key` from the cache or call `fetch`, honoring `control`.
+    /// Returns the response and whether it was a cache hit.
+    async fn cached<T, F>(
+        &self,
+        key: String,
+        model: &str,
+        control: Option<CacheControl>,
+        fetch: F,
+    ) -> Result<(T, bool), LlmError>
+    where
+        T: Serialize + DeserializeOwned,
+        F: std::future::Future<Output = Result<T, LlmError>>,
+    {
+        if control != Some(CacheControl::Refresh)
+            && let Some(hit) = self.lookup(&key).await
+        {
+            match serde_json::from_value(hit) {
+                Ok(response) => return Ok((response, true)),
+                Err(err) => tracing::warn!("Discarding unreadable cached response: {}", err),
+            }
+        }
+
+        // Cache miss, call the real provider
+        let response = fetch.await?;
+        let ttl = match control {
+            Some(CacheControl::Cache { ttl: Some(ttl) }) => ttl,
+            _ => self.config.ttl,
+        };
+        match serde_json::to_value(&response) {
+            Ok(value) => self.remember(key, model, value, ttl).await,
+            Err(err) => tracing::warn!("Response not cacheable: {}", err),
+        }
+        Ok((response, false))
+    }
 }
 
-/// Build a deterministic cache key from a completion request.
-///
-/// Hashes the model name, messages, and response-affecting parameters
-/// (max_tokens, temperature, stop_sequences) via SHA-256. Two requests
-/// with identical content and parameters produce the same key.
-fn cache_key(model: &str, request: &CompletionRequest) -> String {
+/// Collapse formatting noise that does not change meaning: line endings,
+/// trailing whitespace, and leading/trailing blank lines.
+fn normalize_text(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Sort object keys recursively so equal JSON hashes equally.
+fn canonical_json(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let sorted: std::collections::BTreeMap<_, _> = map
+                .iter()
+                .map(|(key, value)| (key.clone(), canonical_json(value)))
+                .collect();
+            serde_json::Value::Object(sorted.into_iter().collect())
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(canonical_json).collect())
+        }
+        other => other.clone(),
+    }
+}
+
+fn normalized_message(message: &ChatMessage) -> serde_json::Value {
+    let mut value = serde_json::to_value(message).unwrap_or_default();
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert(
+            "content".to_string(),
+            serde_json::Value::String(normalize_text(&message.content)),
+        );
+    }
+    canonical_json(&value)
+}
+
+/// Response-affecting parameters beyond the context itself.
+struct KeyParams<'a> {
+    max_tokens: Option<u32>,
+    temperature: Option<f32>,
+    stop_sequences: Option<&'a [String]>,
+    tool_choice: Option<&'a str>,
+}
+
+fn context_key(
+    model: &str,
+    messages: &[ChatMessage],
+    tools: &[ToolDefinition],
+    params: KeyParams<'_>,
+) -> String {
     let mut hasher = Sha256::new();
     hasher.update(model.as_bytes());
-    hasher.update(b"|");
 
-    // Messages are Serialize, so we can deterministically hash them.
-    // serde_json produces stable output for the same input structure.
-    if let Ok(json) = serde_json::to_string(&request.messages) {
-        hasher.update(json.as_bytes());
-    }
+    // The system prompt is hashed as its own segment so reordering it
+    // relative to the conversation still yields a distinct key.
+    hasher.update(b"|system|");
+    for message in messages.iter().filter(|m| m.role == Role::System) {
+        hasher.update(normalize_text(&message.content).as_bytes());
+        hasher.update(b"\x00");
+    }
+    hasher.update(b"|messages|");
+    let conversation: Vec<_> = messages
+        .iter()
+        .filter(|m| m.role != Role::System)
+        .map(normalized_message)
+        .collect();
+    hasher.update(
+        serde_json::Value::Array(conversation)
+            .to_string()
+            .as_bytes(),
+    );
+
+    // Tool order does not change what the model can do.
+    hasher.update(b"|tools|");
+    let mut schema: Vec<_> = tools
+        .iter()
+        .map(|tool| {
+            canonical_json(&serde_json::json!({
+                "name": tool.name,
+                "description": normalize_text(&tool.description),
+                "parameters": tool.parameters,
+            }))
+        })
+        .collect();
+    schema.sort_by_key(|tool| tool["name"].as_str().unwrap_or_default().to_string());
+    hasher.update(serde_json::Value::Array(schema).to_string().as_bytes());
 
     // Include response-affecting parameters so different temperatures,
-    // max_tokens, or stop sequences produce distinct cache keys.
+    // max_tokens, stop sequences, or tool choices produce distinct keys.
     hasher.update(b"|");
-    if let Some(max_tokens) = request.max_tokens {
+    if let Some(max_tokens) = params.max_tokens {
         hasher.update(max_tokens.to_le_bytes());
     }
     hasher.update(b"|");
-    if let Some(temp) = request.temperature {
+    if let Some(temp) = params.temperature {
         hasher.update(temp.to_le_bytes());
     }
     hasher.update(b"|");
-    if let Some(ref stops) = request.stop_sequences {
-        for s in stops {
-            hasher.update(s.as_bytes());
-            hasher.update(b"\x00");
-        }
+    for stop in params.stop_sequences.unwrap_or_default() {
+        hasher.update(stop.as_bytes());
+        hasher.update(b"\x00");
+    }
+    hasher.update(b"|");
+    if let Some(choice) = params.tool_choice {
+        hasher.update(choice.as_bytes());
     }
 
     format!("{:x}", hasher.finalize())
 }
 
+/// Deterministic cache key for a plain completion request.
+fn cache_key(model: &str, request: &CompletionRequest) -> String {
+    context_key(
+        model,
+        &request.messages,
+        &[],
+        KeyParams {
+            max_tokens: request.max_tokens,
+            temperature: request.temperature,
+            stop_sequences: request.stop_sequences.as_deref(),
+            tool_choice: None,
+        },
+    )
+}
+
+/// Deterministic cache key for a tool completion request.
+fn tool_cache_key(model: &str, request: &ToolCompletionRequest) -> String {
+    context_key(
+        model,
+        &request.messages,
+        &request.tools,
+        KeyParams {
+            max_tokens: request.max_tokens,
+            temperature: request.temperature,
+            stop_sequences: None,
+            tool_choice: request.tool_choice.as_deref(),
+        },
+    )
+}
+
 #[async_trait]
 impl LlmProvider for CachedProvider {
     fn model_name(&self) -> &str {
@@ -144,69 +442,61 @@ impl LlmProvider for CachedProvider {
     }
 
     async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, LlmError> {
+        let control = CacheControl::from_metadata(&request.metadata);
+        if control == Some(CacheControl::Bypass) {
+            return self.inner.complete(request).await;
+        }
         let effective_model = self.inner.effective_model_name(request.model.as_deref());
         let key = cache_key(&effective_model, &request);
-        let now = Instant::now();
-
-        // Check cache
-        {
-            let mut guard = self.cache.lock().await;
-            if let Some(entry) = guard.get_mut(&key) {
-                if now.duration_since(entry.created_at) < self.config.ttl {
-                    entry.last_accessed = now;
-                    entry.hit_count += 1;
-                    tracing::debug!(hits = entry.hit_count, "response cache hit");
-                    return Ok(entry.response.clone());
-                }
-                // Expired, remove it
-                guard.remove(&key);
-            }
+        let (mut response, hit) = self
+            .cached(key, &effective_model, control, self.inner.complete(request))
+            .await?;
+        if hit {
+            response.input_tokens = 0;
+            response.output_tokens = 0;
         }
+        Ok(response)
+    }
 
-        // Cache miss, call the real provider
-        let response = self.inner.complete(request).await?;
-
-        // Store in cache
-        {
-            let mut guard = self.cache.lock().await;
-
-            // Evict expired entries
-            guard.retain(|_, entry| now.duration_since(entry.created_at) < self.config.ttl);
-
-            // LRU eviction if over capacity
-            while guard.len() >= self.config.max_entries {
-                let oldest_key = guard
-                    .iter()
-                    .min_by_key(|(_, entry)| entry.last_accessed)
-                    .map(|(k, _)| k.clone());
-
-                if let Some(k) = oldest_key {
-                    guard.remove(&k);
-                } else {
-                    break;
-                }
-            }
-
-            guard.insert(
+    async fn complete_with_tools(
+        &self,
+        request: ToolCompletionRequest,
+    ) -> Result<ToolCompletionResponse, LlmError> {
+        let control = CacheControl::from_metadata(&request.metadata);
+        if !matches!(
+            control,
+            Some(CacheControl::Cache { .. } | CacheControl::Refresh)
+        ) {
+            // Not opted in; tool calls can trigger side effects.
+            return self.inner.complete_with_tools(request).await;
+        }
+        let effective_model = self.inner.effective_model_name(request.model.as_deref());
+        let key = tool_cache_key(&effective_model, &request);
+        let (mut response, hit) = self
+            .cached(
                 key,
-                CacheEntry {
-                    response: response.clone(),
-                    created_at: now,
-                    last_accessed: now,
-                    hit_count: 0,
-                },
-            );
+                &effective_model,
+                control,
+                self.inner.complete_with_tools(request),
+            )
+            .await?;
+        if hit {
+            response.input_tokens = 0;
+            response.output_tokens = 0;
         }
-
         Ok(response)
     }
 
-    async fn complete_with_tools(
+    async fn complete_stream(
         &self,
         request: ToolCompletionRequest,
-    ) -> Result<ToolCompletionResponse, LlmError> {
-        // Never cache tool calls; they can trigger side effects.
-        self.inner.complete_with_tools(request).await
+    ) -> Result<CompletionStream, LlmError> {
+        // Streams are consumed incrementally and never cached.
+        self.inner.complete_stream(request).await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
     }
 
     async fn list_models(&self) -> Result<Vec<String>, LlmError> {
@@ -475,4 +765,166 @@ mod tests {
         let cached = CachedProvider::new(stub.clone(), ResponseCacheConfig::default());
         assert_eq!(cached.model_name(), "stub-model");
     }
+
+    fn tool(name: &str, parameters: serde_json::Value) -> ToolDefinition {
+        ToolDefinition {
+            name: name.to_string(),
+            description: format!("{name} tool"),
+            parameters,
+        }
+    }
+
+    fn tool_request(tools: Vec<ToolDefinition>) -> ToolCompletionRequest {
+        ToolCompletionRequest::new(
+            vec![
+                ChatMessage::system("You are a legal assistant."),
+                ChatMessage::user("summarize the lease"),
+            ],
+            tools,
+        )
+    }
+
+    #[test]
+    fn cache_control_round_trips_through_metadata() {
+        for control in [
+            CacheControl::Cache { ttl: None },
+            CacheControl::Cache {
+                ttl: Some(Duration::from_secs(600)),
+            },
+            CacheControl::Refresh,
+            CacheControl::Bypass,
+        ] {
+            let request = simple_request().with_cache_control(control);
+            assert_eq!(
+                CacheControl::from_metadata(&request.metadata),
+                Some(control)
+            );
+        }
+        assert_eq!(CacheControl::from_metadata(&Default::default()), None);
+
+        let mut metadata = std::collections::HashMap::new();
+        metadata.insert(
+            CACHE_CONTROL_METADATA_KEY.to_string(),
+            "cache:soon".to_string(),
+        );
+        assert_eq!(CacheControl::from_metadata(&metadata), None);
+    }
+
+    #[test]
+    fn cache_key_ignores_formatting_noise() {
+        let mut crlf = simple_request();
+        crlf.messages = vec![
+            ChatMessage::system("Be concise.  \r\nCite sources.\r\n"),
+            ChatMessage::user("hello\n"),
+        ];
+        let mut plain = simple_request();
+        plain.messages = vec![
+            ChatMessage::system("Be concise.\nCite sources."),
+            ChatMessage::user("hello"),
+        ];
+        assert_eq!(cache_key("m", &crlf), cache_key("m", &plain));
+
+        let mut other_system = plain.clone();
+        other_system.messages[0] = ChatMessage::system("Be verbose.");
+        assert_ne!(cache_key("m", &plain), cache_key("m", &other_system));
+    }
+
+    #[test]
+    fn tool_cache_key_covers_schema_but_not_tool_order() {
+        let search = tool(
+            "search",
+            serde_json::json!({ "type": "object", "required": ["q"] }),
+        );
+        let read = tool("read", serde_json::json!({ "type": "object" }));
+        let forward = tool_request(vec![search.clone(), read.clone()]);
+        let reversed = tool_request(vec![read.clone(), search]);
+        assert_eq!(
+            tool_cache_key("m", &forward),
+            tool_cache_key("m", &reversed)
+        );
+
+        let changed = tool_request(vec![
+            tool("search", serde_json::json!({ "type": "object" })),
+            read,
+        ]);
+        assert_ne!(tool_cache_key("m", &forward), tool_cache_key("m", &changed));
+    }
+
+    #[tokio::test]
+    async fn bypass_and_refresh_skip_the_lookup() {
+        let stub = Arc::new(StubLlm::new("cached response"));
+        let cached = CachedProvider::new(stub.clone(), ResponseCacheConfig::default());
+
+        cached
+            .complete(simple_request().with_cache_control(CacheControl::Bypass))
+            .await
+            .unwrap();
+        assert!(cached.is_empty().await);
+
+        cached.complete(simple_request()).await.unwrap();
+        cached
+            .complete(simple_request().with_cache_control(CacheControl::Refresh))
+            .await
+            .unwrap();
+        assert_eq!(stub.calls(), 3);
+        assert_eq!(cached.len().await, 1);
+
+        let hit = cached.complete(simple_request()).await.unwrap();
+        assert_eq!(stub.calls(), 3);
+        assert_eq!((hit.input_tokens, hit.output_tokens), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn opted_in_tool_completions_are_cached() {
+        let stub = Arc::new(StubLlm::new("cached response"));
+        let cached = CachedProvider::new(stub.clone(), ResponseCacheConfig::default());
+        let request = tool_request(vec![tool(
+            "search",
+            serde_json::json!({ "type": "object" }),
+        )])
+        .with_cache_control(CacheControl::Cache { ttl: None });
+
+        let first = cached.complete_with_tools(request.clone()).await.unwrap();
+        assert_eq!(first.input_tokens, 10);
+        let second = cached.complete_with_tools(request).await.unwrap();
+        assert_eq!(stub.calls(), 1);
+        assert_eq!(second.content.as_deref(), Some("cached response"));
+        assert_eq!((second.input_tokens, second.output_tokens), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn call_site_ttl_overrides_config() {
+        let stub = Arc::new(StubLlm::new("cached response"));
+        let cached = CachedProvider::new(stub.clone(), ResponseCacheConfig::default());
+        let request = || {
+            simple_request().with_cache_control(CacheControl::Cache {
+                ttl: Some(Duration::from_millis(1)),
+            })
+        };
+
+        cached.complete(request()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        cached.complete(request()).await.unwrap();
+        assert_eq!(stub.calls(), 2);
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn persisted_entries_survive_a_new_provider() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let stub = Arc::new(StubLlm::new("cached response"));
+
+        let first = CachedProvider::new(stub.clone(), ResponseCacheConfig::default())
+            .with_store(db.clone());
+        first.complete(simple_request()).await.unwrap();
+        assert_eq!(stub.calls(), 1);
+
+        // A fresh provider (e.g. after a restart) has an empty memory tier.
+        let second =
+            CachedProvider::new(stub.clone(), ResponseCacheConfig::default()).with_store(db);
+        let hit = second.complete(simple_request()).await.unwrap();
+        assert_eq!(stub.calls(), 1);
+        assert_eq!(hit.content, "cached response");
+        assert_eq!(second.len().await, 1);
+    }
 }
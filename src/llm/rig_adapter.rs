@@ -4,28 +4,30 @@
 //! `Arc<dyn LlmProvider>` without changing any of the agent, reasoning, or tool code.
 
 use async_trait::async_trait;
+use futures::StreamExt;
 use rig::OneOrMany;
 use rig::completion::{
-    AssistantContent, CompletionModel, CompletionRequest as RigRequest,
+    AssistantContent, CompletionModel, CompletionRequest as RigRequest, GetTokenUsage,
     ToolDefinition as RigToolDefinition, Usage as RigUsage,
 };
 use rig::message::{
     Message as RigMessage, ToolChoice as RigToolChoice, ToolFunction, ToolResult as RigToolResult,
     ToolResultContent, UserContent,
 };
+use rig::streaming::{StreamedAssistantContent, StreamingCompletionResponse, ToolCallDeltaContent};
 use rust_decimal::Decimal;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use serde_json::Value as JsonValue;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::error::LlmError;
 use crate::llm::costs;
 use crate::llm::provider::{
-    ChatMessage, CompletionRequest, CompletionResponse, FinishReason, LlmProvider,
-    ToolCall as IronToolCall, ToolCompletionRequest, ToolCompletionResponse,
-    ToolDefinition as IronToolDefinition,
+    ChatMessage, CompletionRequest, CompletionResponse, CompletionStream, FinishReason,
+    LlmProvider, StreamChunk, ToolCall as IronToolCall, ToolCompletionRequest,
+    ToolCompletionResponse, ToolDefinition as IronToolDefinition,
 };
 
 /// Adapter that wraps a rig-core `CompletionModel` and implements `LlmProvider`.
@@ -513,6 +515,82 @@ where
         })
     }
 
+    async fn complete_stream(
+        &self,
+        request: ToolCompletionRequest,
+    ) -> Result<CompletionStream, LlmError> {
+        let known_tool_names: HashSet<String> =
+            request.tools.iter().map(|t| t.name.clone()).collect();
+
+        let mut messages = request.messages;
+        crate::llm::provider::sanitize_tool_messages(&mut messages);
+        let (preamble, history) = convert_messages(&messages);
+        let tools = convert_tools(&request.tools);
+        let tool_choice = convert_tool_choice(request.tool_choice.as_deref());
+
+        let rig_req = build_rig_request(
+            preamble,
+            history,
+            tools,
+            tool_choice,
+            request.temperature,
+            request.max_tokens,
+        )?;
+
+        let inner = self
+            .model
+            .stream(rig_req)
+            .await
+            .map_err(|e| LlmError::RequestFailed {
+                provider: self.model_name.clone(),
+                reason: e.to_string(),
+            })?;
+
+        let state = RigStreamState {
+            inner,
+            provider: self.model_name.clone(),
+            known_tool_names,
+            tool_indices: HashMap::new(),
+            streamed_calls: HashSet::new(),
+            usage: None,
+            finished: false,
+        };
+        Ok(Box::pin(futures::stream::unfold(
+            state,
+            |mut state| async move {
+                if state.finished {
+                    return None;
+                }
+                loop {
+                    match state.inner.next().await {
+                        Some(Ok(item)) => {
+                            if let Some(chunk) = state.translate(item) {
+                                return Some((Ok(chunk), state));
+                            }
+                        }
+                        Some(Err(e)) => {
+                            state.finished = true;
+                            let err = LlmError::RequestFailed {
+                                provider: state.provider.clone(),
+                                reason: e.to_string(),
+                            };
+                            return Some((Err(err), state));
+                        }
+                        None => {
+                            state.finished = true;
+                            let done = state.done_chunk();
+                            return Some((Ok(done), state));
+                        }
+                    }
+                }
+            },
+        )))
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
     fn active_model_name(&self) -> String {
         self.model_name.clone()
     }
@@ -533,6 +611,96 @@ where
     }
 }
 
+/// Translates a rig-core stream into [`StreamChunk`]s.
+struct RigStreamState<R: Clone + Unpin + GetTokenUsage> {
+    inner: StreamingCompletionResponse<R>,
+    provider: String,
+    known_tool_names: HashSet<String>,
+    /// rig's internal call id -> OpenAI-style tool call index.
+    tool_indices: HashMap<String, u32>,
+    /// Calls for which argument/name deltas have already been forwarded.
+    streamed_calls: HashSet<String>,
+    usage: Option<RigUsage>,
+    finished: bool,
+}
+
+impl<R: Clone + Unpin + GetTokenUsage> RigStreamState<R> {
+    fn tool_index(&mut self, internal_call_id: &str) -> u32 {
+        let next = self.tool_indices.len() as u32;
+        *self
+            .tool_indices
+            .entry(internal_call_id.to_string())
+            .or_insert(next)
+    }
+
+    fn translate(&mut self, item: StreamedAssistantContent<R>) -> Option<StreamChunk> {
+        match item {
+            StreamedAssistantContent::Text(t) if !t.text.is_empty() => {
+                Some(StreamChunk::Text(t.text))
+            }
+            StreamedAssistantContent::ToolCallDelta {
+                id,
+                internal_call_id,
+                content,
+            } => {
+                let index = self.tool_index(&internal_call_id);
+                let first = self.streamed_calls.insert(internal_call_id);
+                let id = (first && !id.is_empty()).then_some(id);
+                Some(match content {
+                    ToolCallDeltaContent::Name(name) => StreamChunk::ToolCall {
+                        index,
+                        id,
+                        name: Some(normalize_tool_name(&name, &self.known_tool_names)),
+                        arguments: None,
+                    },
+                    ToolCallDeltaContent::Delta(arguments) => StreamChunk::ToolCall {
+                        index,
+                        id,
+                        name: None,
+                        arguments: Some(arguments),
+                    },
+                })
+            }
+            StreamedAssistantContent::ToolCall {
+                tool_call,
+                internal_call_id,
+            } => {
+                // Providers that streamed deltas also emit the assembled call;
+                // only forward it when nothing was streamed for this call.
+                let index = self.tool_index(&internal_call_id);
+                (!self.streamed_calls.contains(&internal_call_id)).then(|| StreamChunk::ToolCall {
+                    index,
+                    id: Some(tool_call.id),
+                    name: Some(normalize_tool_name(
+                        &tool_call.function.name,
+                        &self.known_tool_names,
+                    )),
+                    arguments: Some(tool_call.function.arguments.to_string()),
+                })
+            }
+            StreamedAssistantContent::Final(response) => {
+                self.usage = response.token_usage();
+                None
+            }
+            // Reasoning is not surfaced to callers; empty text is noise.
+            _ => None,
+        }
+    }
+
+    fn done_chunk(&self) -> StreamChunk {
+        let usage = self.usage.unwrap_or_default();
+        StreamChunk::Done {
+            input_tokens: saturate_u32(usage.input_tokens),
+            output_tokens: saturate_u32(usage.output_tokens),
+            finish_reason: if self.tool_indices.is_empty() {
+                FinishReason::Stop
+            } else {
+                FinishReason::ToolUse
+            },
+        }
+    }
+}
+
 /// Normalize a tool call name returned by an OpenAI-compatible provider.
 ///
 /// Some proxies (e.g. VibeProxy) prepend `proxy_` to tool names.
@@ -843,6 +1011,87 @@ mod tests {
 
     // -- normalize_tool_name tests --
 
+    fn stream_state() -> RigStreamState<()> {
+        RigStreamState {
+            inner: StreamingCompletionResponse::stream(Box::pin(futures::stream::empty())),
+            provider: "test".to_string(),
+            known_tool_names: HashSet::from(["search".to_string()]),
+            tool_indices: HashMap::new(),
+            streamed_calls: HashSet::new(),
+            usage: None,
+            finished: false,
+        }
+    }
+
+    #[test]
+    fn test_stream_translate_skips_assembled_call_after_deltas() {
+        let mut state = stream_state();
+        let name = state.translate(StreamedAssistantContent::ToolCallDelta {
+            id: "call_a".to_string(),
+            internal_call_id: "a".to_string(),
+            content: ToolCallDeltaContent::Name("proxy_search".to_string()),
+        });
+        assert_eq!(
+            name,
+            Some(StreamChunk::ToolCall {
+                index: 0,
+                id: Some("call_a".to_string()),
+                name: Some("search".to_string()),
+                arguments: None,
+            })
+        );
+        let args = state.translate(StreamedAssistantContent::ToolCallDelta {
+            id: "call_a".to_string(),
+            internal_call_id: "a".to_string(),
+            content: ToolCallDeltaContent::Delta("{}".to_string()),
+        });
+        assert_eq!(
+            args,
+            Some(StreamChunk::ToolCall {
+                index: 0,
+                id: None,
+                name: None,
+                arguments: Some("{}".to_string()),
+            })
+        );
+        let assembled = rig::message::ToolCall::new(
+            "call_a".to_string(),
+            ToolFunction::new("search".to_string(), serde_json::json!({})),
+        );
+        assert_eq!(
+            state.translate(StreamedAssistantContent::ToolCall {
+                tool_call: assembled,
+                internal_call_id: "a".to_string(),
+            }),
+            None
+        );
+
+        // A call that arrives only in assembled form gets the next index.
+        let whole = rig::message::ToolCall::new(
+            "call_b".to_string(),
+            ToolFunction::new("search".to_string(), serde_json::json!({"q": "x"})),
+        );
+        assert_eq!(
+            state.translate(StreamedAssistantContent::ToolCall {
+                tool_call: whole,
+                internal_call_id: "b".to_string(),
+            }),
+            Some(StreamChunk::ToolCall {
+                index: 1,
+                id: Some("call_b".to_string()),
+                name: Some("search".to_string()),
+                arguments: Some(r#"{"q":"x"}"#.to_string()),
+            })
+        );
+        assert!(matches!(
+            state.done_chunk(),
+            StreamChunk::Done {
+                finish_reason: FinishReason::ToolUse,
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn test_normalize_tool_name_exact_match() {
         let known = HashSet::from(["echo".to_string(), "list_jobs".to_string()]);
@@ -0,0 +1,306 @@
+//! Task- and confidentiality-aware routing across LLM providers.
+//!
+//! Callers tag requests with an [`LlmTask`] and, for matter work, the matter's
+//! confidentiality level, both carried in request metadata.
+//! [`TaskRoutingProvider`] sends each request to the first configured route
+//! that matches. Each route owns its own provider chain, normally a
+//! [`FailoverProvider`](crate::llm::FailoverProvider) over the route's models
+//! in priority order, so an unhealthy model is cooled down and skipped.
+//! Untagged and unmatched requests go to the default provider.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+
+use crate::error::LlmError;
+use crate::llm::provider::{
+    CompletionRequest, CompletionResponse, CompletionStream, LlmProvider, ModelMetadata,
+    ToolCompletionRequest, ToolCompletionResponse,
+};
+
+/// Request metadata key holding the [`LlmTask`] tag.
+pub const TASK_METADATA_KEY: &str = "llm_task";
+
+/// Request metadata key holding the active matter's confidentiality level.
+pub const CONFIDENTIALITY_METADATA_KEY: &str = "matter_confidentiality";
+
+/// Kind of work a request performs, used to pick a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LlmTask {
+    /// Long-form legal writing: memos, letters, translations.
+    Drafting,
+    /// Condensing existing material: compaction, digests.
+    Summarization,
+    /// Structured output from documents: entities, classifications.
+    Extraction,
+}
+
+impl LlmTask {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Drafting => "drafting",
+            Self::Summarization => "summarization",
+            Self::Extraction => "extraction",
+        }
+    }
+}
+
+impl std::str::FromStr for LlmTask {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "drafting" | "draft" => Ok(Self::Drafting),
+            "summarization" | "summarize" | "summary" => Ok(Self::Summarization),
+            "extraction" | "extract" => Ok(Self::Extraction),
+            other => Err(format!(
+                "unknown LLM task '{other}', expected one of: drafting, summarization, extraction"
+            )),
+        }
+    }
+}
+
+/// A routing rule paired with the provider that serves it.
+pub struct TaskRoute {
+    task: Option<LlmTask>,
+    confidentiality: Option<String>,
+    provider: Arc<dyn LlmProvider>,
+}
+
+impl TaskRoute {
+    /// `None` for `task` or `confidentiality` matches any value, including
+    /// requests that carry no tag.
+    pub fn new(
+        task: Option<LlmTask>,
+        confidentiality: Option<String>,
+        provider: Arc<dyn LlmProvider>,
+    ) -> Self {
+        Self {
+            task,
+            confidentiality: confidentiality
+                .map(|level| level.trim().to_ascii_lowercase())
+                .filter(|level| !level.is_empty()),
+            provider,
+        }
+    }
+
+    fn matches(&self, task: Option<LlmTask>, confidentiality: Option<&str>) -> bool {
+        let task_matches = self.task.is_none_or(|want| task == Some(want));
+        let confidentiality_matches = match self.confidentiality.as_deref() {
+            None => true,
+            Some(want) => confidentiality.is_some_and(|level| level.eq_ignore_ascii_case(want)),
+        };
+        task_matches && confidentiality_matches
+    }
+}
+
+/// Routes requests to per-task, per-confidentiality provider chains.
+///
+/// Routes are checked in order and the first match wins, so more specific
+/// rules belong first.
+pub struct TaskRoutingProvider {
+    default: Arc<dyn LlmProvider>,
+    routes: Vec<TaskRoute>,
+}
+
+impl TaskRoutingProvider {
+    pub fn new(default: Arc<dyn LlmProvider>, routes: Vec<TaskRoute>) -> Self {
+        Self { default, routes }
+    }
+
+    fn provider_for(&self, metadata: &HashMap<String, String>) -> &Arc<dyn LlmProvider> {
+        let task = metadata
+            .get(TASK_METADATA_KEY)
+            .and_then(|raw| raw.parse::<LlmTask>().ok());
+        let confidentiality = metadata
+            .get(CONFIDENTIALITY_METADATA_KEY)
+            .map(|level| level.trim());
+        match self
+            .routes
+            .iter()
+            .find(|route| route.matches(task, confidentiality))
+        {
+            Some(route) => {
+                tracing::debug!(
+                    task = task.map(|t| t.as_str()),
+                    confidentiality,
+                    model = %route.provider.model_name(),
+                    "Task routing: matched route"
+                );
+                &route.provider
+            }
+            None => &self.default,
+        }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for TaskRoutingProvider {
+    fn model_name(&self) -> &str {
+        self.default.model_name()
+    }
+
+    fn cost_per_token(&self) -> (Decimal, Decimal) {
+        self.default.cost_per_token()
+    }
+
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, LlmError> {
+        self.provider_for(&request.metadata).complete(request).await
+    }
+
+    async fn complete_with_tools(
+        &self,
+        request: ToolCompletionRequest,
+    ) -> Result<ToolCompletionResponse, LlmError> {
+        self.provider_for(&request.metadata)
+            .complete_with_tools(request)
+            .await
+    }
+
+    async fn complete_stream(
+        &self,
+        request: ToolCompletionRequest,
+    ) -> Result<CompletionStream, LlmError> {
+        self.provider_for(&request.metadata)
+            .complete_stream(request)
+            .await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.default.supports_streaming()
+    }
+
+    async fn list_models(&self) -> Result<Vec<String>, LlmError> {
+        self.default.list_models().await
+    }
+
+    async fn model_metadata(&self) -> Result<ModelMetadata, LlmError> {
+        self.default.model_metadata().await
+    }
+
+    fn active_model_name(&self) -> String {
+        self.default.active_model_name()
+    }
+
+    fn set_model(&self, model: &str) -> Result<(), LlmError> {
+        self.default.set_model(model)
+    }
+
+    fn calculate_cost(&self, input_tokens: u32, output_tokens: u32) -> Decimal {
+        self.default.calculate_cost(input_tokens, output_tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::provider::ChatMessage;
+    use crate::llm::{CooldownConfig, FailoverProvider};
+    use crate::testing::StubLlm;
+
+    fn request(task: Option<LlmTask>, confidentiality: Option<&str>) -> CompletionRequest {
+        let mut request = CompletionRequest::new(vec![ChatMessage::user("hello")]);
+        if let Some(task) = task {
+            request = request.with_task(task);
+        }
+        if let Some(level) = confidentiality {
+            request
+                .metadata
+                .insert(CONFIDENTIALITY_METADATA_KEY.to_string(), level.to_string());
+        }
+        request
+    }
+
+    #[test]
+    fn task_names_parse_with_aliases() {
+        assert_eq!("Drafting".parse::<LlmTask>(), Ok(LlmTask::Drafting));
+        assert_eq!("summarize".parse::<LlmTask>(), Ok(LlmTask::Summarization));
+        assert_eq!("extract".parse::<LlmTask>(), Ok(LlmTask::Extraction));
+        assert!("chat".parse::<LlmTask>().is_err());
+    }
+
+    #[tokio::test]
+    async fn first_matching_route_wins_and_untagged_uses_default() {
+        let default = Arc::new(StubLlm::new("default"));
+        let privileged = Arc::new(StubLlm::new("local"));
+        let drafting = Arc::new(StubLlm::new("drafting"));
+        let router = TaskRoutingProvider::new(
+            default.clone(),
+            vec![
+                TaskRoute::new(
+                    None,
+                    Some("Attorney-Client-Privileged".to_string()),
+                    privileged.clone(),
+                ),
+                TaskRoute::new(Some(LlmTask::Drafting), None, drafting.clone()),
+            ],
+        );
+
+        let out = router
+            .complete(request(Some(LlmTask::Drafting), None))
+            .await
+            .expect("drafting");
+        assert_eq!(out.content, "drafting");
+
+        let out = router
+            .complete(request(
+                Some(LlmTask::Drafting),
+                Some("attorney-client-privileged"),
+            ))
+            .await
+            .expect("privileged drafting");
+        assert_eq!(out.content, "local");
+
+        let out = router
+            .complete(request(Some(LlmTask::Summarization), Some("public")))
+            .await
+            .expect("unmatched");
+        assert_eq!(out.content, "default");
+        let out = router
+            .complete(request(None, None))
+            .await
+            .expect("untagged");
+        assert_eq!(out.content, "default");
+
+        assert_eq!(default.calls(), 2);
+        assert_eq!(privileged.calls(), 1);
+        assert_eq!(drafting.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn route_fails_over_to_next_model_without_touching_default() {
+        let default = Arc::new(StubLlm::new("default"));
+        let primary = Arc::new(StubLlm::failing("extract-primary"));
+        let backup = Arc::new(StubLlm::new("backup").with_model_name("extract-backup"));
+        let chain = FailoverProvider::with_cooldown(
+            vec![primary.clone(), backup.clone()],
+            CooldownConfig {
+                cooldown_duration: std::time::Duration::from_secs(60),
+                failure_threshold: 1,
+            },
+        )
+        .expect("chain");
+        let router = TaskRoutingProvider::new(
+            default.clone(),
+            vec![TaskRoute::new(
+                Some(LlmTask::Extraction),
+                None,
+                Arc::new(chain),
+            )],
+        );
+
+        for _ in 0..2 {
+            let out = router
+                .complete(request(Some(LlmTask::Extraction), None))
+                .await
+                .expect("extraction");
+            assert_eq!(out.content, "backup");
+        }
+        // The failing model is cooled down after its first failure.
+        assert_eq!(primary.calls(), 1);
+        assert_eq!(backup.calls(), 2);
+        assert_eq!(default.calls(), 0);
+    }
+}
@@ -9,8 +9,8 @@ use clawyer::{
     agent::{Agent, AgentDeps},
     app::{AppBuilder, AppBuilderFlags},
     channels::{
-        ChannelManager, GatewayChannel, HttpChannel, ReplChannel, SignalChannel, WebhookServer,
-        WebhookServerConfig,
+        ChannelManager, GatewayChannel, HttpChannel, ReplChannel, SignalChannel, SmsChannel,
+        WebhookServer, WebhookServerConfig,
         wasm::{
             RegisteredEndpoint, SharedWasmChannel, WasmChannelLoader, WasmChannelRouter,
             WasmChannelRuntime, WasmChannelRuntimeConfig, create_wasm_channel_router,
@@ -18,8 +18,8 @@ use clawyer::{
         web::log_layer::LogBroadcaster,
     },
     cli::{
-        Cli, Command, LegalProfileArg, run_backup_command, run_mcp_command, run_pairing_command,
-        run_service_command, run_status_command, run_tool_command,
+        Cli, Command, LegalProfileArg, McpTransportArg, run_backup_command, run_mcp_command,
+        run_pairing_command, run_service_command, run_status_command, run_tool_command,
     },
     config::Config,
     hooks::bootstrap_hooks,
@@ -62,6 +62,12 @@ async fn main() -> anyhow::Result<()> {
             init_cli_tracing();
             return run_backup_command(backup_cmd.clone()).await;
         }
+        Some(Command::Db(db_cmd)) => {
+            init_cli_tracing();
+            let _ = dotenvy::dotenv();
+            clawyer::bootstrap::load_ironclaw_env();
+            return clawyer::cli::run_db_command(db_cmd.clone()).await;
+        }
         Some(Command::Registry(registry_cmd)) => {
             init_cli_tracing();
             return clawyer::cli::run_registry_command(registry_cmd.clone()).await;
@@ -88,6 +94,12 @@ async fn main() -> anyhow::Result<()> {
             clawyer::bootstrap::load_ironclaw_env();
             return clawyer::cli::run_doctor_command().await;
         }
+        Some(Command::Loadtest(args)) => {
+            init_cli_tracing();
+            let _ = dotenvy::dotenv();
+            clawyer::bootstrap::load_ironclaw_env();
+            return clawyer::cli::run_loadtest_command(args.clone()).await;
+        }
         Some(Command::Status) => {
             init_cli_tracing();
             let _ = dotenvy::dotenv();
@@ -206,6 +218,10 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::info!("Starting cLawyer...");
     tracing::info!("Loaded configuration for agent: {}", config.agent.name);
+
+    if config.observability.metrics_enabled {
+        clawyer::observability::metrics::install_prometheus_recorder();
+    }
     tracing::info!("LLM backend: {}", config.llm.backend);
 
     // ── Phase 1-5: Build all core components via AppBuilder ────────────
@@ -221,6 +237,12 @@ async fn main() -> anyhow::Result<()> {
     .build_all()
     .await?;
 
+    // MCP server mode replaces the normal channels entirely. It runs before
+    // any interactive auth so nothing but JSON-RPC is written to stdout.
+    if let Some(transport) = cli.mcp_serve {
+        return run_mcp_server(&cli, transport, components).await;
+    }
+
     let mut config = components.config;
     apply_cli_legal_overrides(&cli, &mut config);
 
@@ -290,8 +312,15 @@ async fn main() -> anyhow::Result<()> {
                 claude_code_max_turns: config.claude_code.max_turns,
                 claude_code_memory_limit_mb: config.claude_code.memory_limit_mb,
                 claude_code_allowed_tools: config.claude_code.allowed_tools.clone(),
+                max_concurrent_jobs: config.sandbox.max_concurrent_jobs,
+                max_jobs_per_user: config.sandbox.max_jobs_per_user,
+                worker_retry: config.sandbox.retry.clone(),
+                claude_code_retry: config.claude_code.retry.clone(),
             };
-            let jm = Arc::new(ContainerJobManager::new(job_config, token_store.clone()));
+            let jm = Arc::new(
+                ContainerJobManager::new(job_config, token_store.clone())
+                    .with_store(components.db.clone()),
+            );
 
             // Start the orchestrator internal API in the background
             let orchestrator_state = OrchestratorState {
@@ -303,6 +332,7 @@ async fn main() -> anyhow::Result<()> {
                 store: components.db.clone(),
                 secrets_store: components.secrets_store.clone(),
                 user_id: "default".to_string(),
+                cost_guard: Some(Arc::clone(&components.cost_guard)),
             };
 
             tokio::spawn(async move {
@@ -310,6 +340,12 @@ async fn main() -> anyhow::Result<()> {
                     tracing::error!("Orchestrator API failed: {}", e);
                 }
             });
+            if let Some(db) = components.db.clone() {
+                clawyer::orchestrator::artifacts::spawn_artifact_gc(
+                    db,
+                    config.sandbox.artifact_retention_days,
+                );
+            }
 
             if config.claude_code.enabled {
                 tracing::info!(
@@ -326,6 +362,10 @@ async fn main() -> anyhow::Result<()> {
     // ── Channel setup ──────────────────────────────────────────────────
 
     let channels = ChannelManager::new();
+    if config.legal.supervised_mode {
+        channels.review_queue().set_enabled(true);
+        tracing::info!("Supervised mode: outbound messages to external channels require review");
+    }
     let mut channel_names: Vec<String> = Vec::new();
     let mut loaded_wasm_channel_names: Vec<String> = Vec::new();
     #[allow(clippy::type_complexity)]
@@ -366,6 +406,8 @@ async fn main() -> anyhow::Result<()> {
         let wasm_result = setup_wasm_channels(
             &config,
             &components.secrets_store,
+            components.db.as_ref(),
+            components.workspace.as_ref(),
             components.extension_manager.as_ref(),
         )
         .await;
@@ -391,7 +433,18 @@ async fn main() -> anyhow::Result<()> {
     if !cli.cli_only
         && let Some(ref signal_config) = config.channels.signal
     {
-        let signal_channel = SignalChannel::new(signal_config.clone())?;
+        let mut signal_channel = SignalChannel::new(signal_config.clone())?;
+        if !signal_config.matter_groups.is_empty() {
+            match components.workspace {
+                Some(ref ws) if config.legal.enabled => {
+                    signal_channel =
+                        signal_channel.with_matter_log(Arc::clone(ws), &config.legal.matter_root);
+                }
+                _ => tracing::warn!(
+                    "SIGNAL_MATTER_GROUPS is set but legal mode or the workspace is unavailable; matter groups will not be logged"
+                ),
+            }
+        }
         channel_names.push("signal".to_string());
         channels.add(Box::new(signal_channel)).await;
         let safe_url = SignalChannel::redact_url(&signal_config.http_url);
@@ -406,6 +459,25 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
+    // Add SMS channel if configured and not CLI-only mode. Consent records
+    // need the database, so the channel stays off without one.
+    if !cli.cli_only
+        && let Some(ref sms_config) = config.channels.sms
+    {
+        match components.db {
+            Some(ref db) => {
+                let sms_channel = SmsChannel::new(sms_config.clone(), Arc::clone(db))?;
+                webhook_routes.push(sms_channel.routes());
+                channel_names.push("sms".to_string());
+                channels.add(Box::new(sms_channel)).await;
+                tracing::info!(from = %sms_config.from_number, "SMS channel enabled");
+            }
+            None => tracing::warn!(
+                "TWILIO_ACCOUNT_SID is set but no database is available; SMS channel disabled"
+            ),
+        }
+    }
+
     // Add HTTP channel if configured and not CLI-only mode.
     let mut webhook_server_addr: Option<std::net::SocketAddr> = None;
     if !cli.cli_only
@@ -489,9 +561,69 @@ async fn main() -> anyhow::Result<()> {
         },
         components.secrets_store.clone(),
     );
+    if let (Some(jm), Some(ws)) = (&container_job_manager, &components.workspace) {
+        components
+            .tools
+            .register_run_code_tool(Arc::clone(jm), Arc::clone(ws));
+    }
+
+    // ── Workspace git mirror ───────────────────────────────────────────
+
+    let git_mirror = match (config.git_mirror.to_mirror(), &components.workspace) {
+        (Some(mirror), Some(ws)) => match mirror.init().await {
+            Ok(()) => {
+                let mirror = Arc::new(mirror);
+                Arc::clone(&mirror).spawn_sync_loop(
+                    Arc::clone(ws),
+                    std::time::Duration::from_secs(config.git_mirror.interval_secs),
+                );
+                tracing::info!(
+                    "Workspace git mirror enabled at {}",
+                    mirror.repo_path().display()
+                );
+                Some(mirror)
+            }
+            Err(e) => {
+                tracing::warn!("Workspace git mirror disabled: {}", e);
+                None
+            }
+        },
+        _ => None,
+    };
+
+    // ── Scheduled backups ──────────────────────────────────────────────
+
+    if config.backup.interval().is_some() {
+        match (
+            &components.db,
+            &components.workspace,
+            config.secrets.master_key(),
+        ) {
+            (Some(db), Some(ws), Some(master_key)) => {
+                clawyer::legal::backup_schedule::spawn_backup_scheduler(
+                    Arc::clone(db),
+                    Arc::clone(ws),
+                    "default".to_string(),
+                    master_key.clone(),
+                    config.legal.matter_root.clone(),
+                    &config.backup,
+                );
+                tracing::info!(
+                    "Scheduled backups every {}h (keeping {})",
+                    config.backup.interval_hours,
+                    config.backup.retention_count
+                );
+            }
+            (_, _, None) => {
+                tracing::warn!("Scheduled backups disabled: SECRETS_MASTER_KEY is not configured")
+            }
+            _ => tracing::warn!("Scheduled backups disabled: database or workspace unavailable"),
+        }
+    }
 
     // ── Gateway channel ────────────────────────────────────────────────
 
+    let routine_engine_slot = clawyer::agent::RoutineEngineSlot::default();
     let mut gateway_url: Option<String> = None;
     if let Some(ref gw_config) = config.channels.gateway {
         let runtime_facts = clawyer::compliance::ComplianceRuntimeFacts {
@@ -531,6 +663,34 @@ async fn main() -> anyhow::Result<()> {
             gw = gw.with_skill_catalog(Arc::clone(sc));
         }
         gw = gw.with_cost_guard(Arc::clone(&components.cost_guard));
+        gw = gw.with_review_queue(channels.review_queue());
+        gw = gw.with_routine_engine(routine_engine_slot.clone());
+        let webhooks = match components.db {
+            Some(ref d) => Some(Arc::new(
+                clawyer::integrations::WebhookDispatcher::load(Arc::clone(d), &gw_config.user_id)
+                    .await,
+            )),
+            None => None,
+        };
+        if let Some(ref webhooks) = webhooks {
+            gw = gw.with_webhooks(Arc::clone(webhooks));
+        }
+        if let Some(ref mirror) = git_mirror {
+            gw = gw.with_git_mirror(Arc::clone(mirror));
+        }
+        {
+            let slot = routine_engine_slot.clone();
+            gw.state()
+                .events
+                .spawn_subscriber("routines", move |event| {
+                    let slot = slot.clone();
+                    async move {
+                        if let Some(engine) = slot.get() {
+                            engine.check_domain_event(&event).await;
+                        }
+                    }
+                });
+        }
         if config.sandbox.enabled {
             gw = gw.with_prompt_queue(Arc::clone(&prompt_queue));
 
@@ -539,6 +699,17 @@ async fn main() -> anyhow::Result<()> {
                 let gw_state = Arc::clone(gw.state());
                 tokio::spawn(async move {
                     while let Ok((_job_id, event)) = rx.recv().await {
+                        if let clawyer::channels::web::types::SseEvent::JobResult {
+                            ref job_id,
+                            ref status,
+                            ..
+                        } = event
+                        {
+                            gw_state.publish_event(clawyer::events::DomainEvent::JobCompleted {
+                                job_id: job_id.clone(),
+                                status: status.clone(),
+                            });
+                        }
                         gw_state.sse.broadcast(event);
                     }
                 });
@@ -639,6 +810,7 @@ async fn main() -> anyhow::Result<()> {
         legal_config: config.legal.clone(),
         hooks: components.hooks,
         cost_guard: components.cost_guard,
+        routine_engine_slot: Some(routine_engine_slot),
     };
 
     let agent = Agent::new(
@@ -707,7 +879,62 @@ fn apply_cli_legal_overrides(cli: &Cli, config: &mut Config) {
 }
 
 fn should_run_startup_onboarding(cli: &Cli) -> bool {
-    !cli.no_onboard && !cli.no_db && !cli.headless
+    !cli.no_onboard && !cli.no_db && !cli.headless && cli.mcp_serve.is_none()
+}
+
+/// Serve the tool registry, workspace, and matters over MCP until the
+/// transport closes.
+async fn run_mcp_server(
+    cli: &Cli,
+    transport: McpTransportArg,
+    components: clawyer::app::AppComponents,
+) -> anyhow::Result<()> {
+    let mut config = components.config;
+    apply_cli_legal_overrides(cli, &mut config);
+
+    let active_tool_names = components.tools.list().await;
+    bootstrap_hooks(
+        &components.hooks,
+        components.workspace.as_ref(),
+        &config.wasm.tools_dir,
+        &config.channels.wasm_channels_dir,
+        &active_tool_names,
+        &[],
+        &components.dev_loaded_tool_names,
+    )
+    .await;
+
+    let mut server = clawyer::tools::mcp::McpServer::new(
+        Arc::clone(&components.tools),
+        Arc::clone(&components.safety),
+        "default",
+    )
+    .with_hooks(Arc::clone(&components.hooks))
+    .with_legal_config(config.legal);
+    if let Some(ref ws) = components.workspace {
+        server = server.with_workspace(Arc::clone(ws));
+    }
+    if let Some(db) = components.db {
+        server = server.with_store(db);
+    }
+    let server = Arc::new(server);
+
+    match transport {
+        McpTransportArg::Stdio => server.serve_stdio().await?,
+        McpTransportArg::Sse => {
+            let token = match cli.mcp_token.clone() {
+                Some(token) => token,
+                None => {
+                    let token = uuid::Uuid::new_v4().simple().to_string();
+                    eprintln!("MCP SSE token (set MCP_SERVE_TOKEN to pin it): {token}");
+                    token
+                }
+            };
+            eprintln!("MCP SSE endpoint: http://{}/sse", cli.mcp_listen);
+            server.serve_sse(cli.mcp_listen, token).await?;
+        }
+    }
+    Ok(())
 }
 
 fn should_enable_repl_channel(cli: &Cli, cli_enabled: bool) -> bool {
@@ -896,6 +1123,8 @@ struct WasmChannelSetup {
 async fn setup_wasm_channels(
     config: &clawyer::config::Config,
     secrets_store: &Option<Arc<dyn SecretsStore + Send + Sync>>,
+    db: Option<&Arc<dyn clawyer::db::Database>>,
+    workspace: Option<&Arc<clawyer::workspace::Workspace>>,
     extension_manager: Option<&Arc<clawyer::extensions::ExtensionManager>>,
 ) -> Option<WasmChannelSetup> {
     let runtime = match WasmChannelRuntime::new(WasmChannelRuntimeConfig::default()) {
@@ -907,7 +1136,26 @@ async fn setup_wasm_channels(
     };
 
     let pairing_store = Arc::new(PairingStore::new());
-    let loader = WasmChannelLoader::new(Arc::clone(&runtime), Arc::clone(&pairing_store));
+    let mut loader = WasmChannelLoader::new(Arc::clone(&runtime), Arc::clone(&pairing_store));
+    if let Some(db) = db {
+        let settings: Arc<dyn clawyer::db::SettingsStore> = db.clone();
+        loader = loader.with_settings_store(settings, "default");
+    }
+    if let Some(workspace) = workspace {
+        loader = loader.with_workspace(Arc::clone(workspace));
+    }
+    if let Some(transcriber) = config.transcription.create_transcriber() {
+        let mut service = clawyer::integrations::TranscriptionService::new(transcriber);
+        if let (Some(db), Some(workspace)) = (db, workspace) {
+            service = service.with_voice_note_log(clawyer::integrations::VoiceNoteLog::new(
+                Arc::clone(workspace),
+                Arc::clone(db),
+                "default",
+                config.legal.matter_root.clone(),
+            ));
+        }
+        loader = loader.with_transcription(Arc::new(service));
+    }
 
     let results = match loader
         .load_from_dir(&config.channels.wasm_channels_dir)
@@ -943,6 +1191,17 @@ async fn setup_wasm_channels(
         };
 
         let secret_header = loaded.webhook_secret_header().map(|s| s.to_string());
+        let verify_token_param = loaded.webhook_verify_token_param().map(|s| s.to_string());
+
+        // Body-signing key for channels whose provider signs webhooks (e.g., WhatsApp)
+        let signing = match (loaded.webhook_signature(), secrets_store) {
+            (Some((header, name)), Some(secrets)) => secrets
+                .get_decrypted("default", name)
+                .await
+                .ok()
+                .map(|s| (header.to_string(), s.expose().to_string())),
+            _ => None,
+        };
 
         let webhook_path = format!("/webhook/{}", channel_name);
         let endpoints = vec![RegisteredEndpoint {
@@ -1004,6 +1263,16 @@ async fn setup_wasm_channels(
                 secret_header,
             )
             .await;
+        if let Some(param) = verify_token_param {
+            wasm_router
+                .set_verify_token_param(&channel_name, param)
+                .await;
+        }
+        if let Some((header, secret)) = signing {
+            wasm_router
+                .set_signing_secret(&channel_name, header, secret)
+                .await;
+        }
         has_webhook_channels = true;
 
         if let Some(secrets) = secrets_store {
@@ -0,0 +1,132 @@
+//! Notification inbox.
+//!
+//! Routine outputs, deadline alerts, and system warnings meant for the
+//! attorney are written to the `notifications` table by [`notify`] and pushed
+//! to live subscribers (the web gateway forwards them over SSE). A
+//! notification stays in the inbox until it is acknowledged; a snoozed one
+//! drops out of the unread count until its snooze passes.
+//!
+//! Like the audit logger, the push channel is process-wide, so the routine
+//! engine and job manager can post without holding a gateway handle.
+//!
+//! Notifications addressed to a person (e.g. an @mention in a matter note)
+//! go through [`notify_user`], which also hands a copy to the agent's
+//! channel manager when the user has chosen a delivery channel.
+
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::db::{Database, NewNotificationParams, NotificationRecord};
+
+/// Notifications buffered per subscriber before the slowest one starts lagging.
+const PUSH_CAPACITY: usize = 64;
+
+/// Per-user setting naming the channel notifications are also delivered on.
+pub const DELIVERY_SETTING_KEY: &str = "notifications.delivery";
+
+static PUSH: OnceLock<broadcast::Sender<NotificationRecord>> = OnceLock::new();
+static DELIVERY: OnceLock<broadcast::Sender<ChannelDelivery>> = OnceLock::new();
+
+/// Where a user wants notifications delivered besides the inbox.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeliveryPreference {
+    /// Channel name as registered with the channel manager (e.g. `telegram`).
+    pub channel: String,
+    /// Address on that channel; the user ID when unset.
+    #[serde(default)]
+    pub recipient: Option<String>,
+}
+
+/// A notification bound for a messaging channel.
+#[derive(Debug, Clone)]
+pub struct ChannelDelivery {
+    pub channel: String,
+    pub recipient: String,
+    pub message: String,
+}
+
+fn push_channel() -> &'static broadcast::Sender<NotificationRecord> {
+    PUSH.get_or_init(|| broadcast::channel(PUSH_CAPACITY).0)
+}
+
+fn delivery_channel() -> &'static broadcast::Sender<ChannelDelivery> {
+    DELIVERY.get_or_init(|| broadcast::channel(PUSH_CAPACITY).0)
+}
+
+/// Receive every notification posted after this call.
+pub fn subscribe() -> broadcast::Receiver<NotificationRecord> {
+    push_channel().subscribe()
+}
+
+/// Receive channel deliveries requested after this call.
+pub fn subscribe_deliveries() -> broadcast::Receiver<ChannelDelivery> {
+    delivery_channel().subscribe()
+}
+
+/// The user's delivery channel, if they have chosen one.
+pub async fn delivery_preference(
+    store: &dyn Database,
+    user_id: &str,
+) -> Option<DeliveryPreference> {
+    match store.get_setting(user_id, DELIVERY_SETTING_KEY).await {
+        Ok(value) => value.and_then(|value| serde_json::from_value(value).ok()),
+        Err(e) => {
+            tracing::warn!(user_id, error = %e, "Failed to load notification delivery setting");
+            None
+        }
+    }
+}
+
+/// Store a notification and push it to live subscribers.
+///
+/// Failures are logged rather than returned: a notification that cannot be
+/// stored must not fail the routine run or job that produced it.
+pub async fn notify(
+    store: &dyn Database,
+    params: NewNotificationParams,
+) -> Option<NotificationRecord> {
+    match store.create_notification(&params).await {
+        Ok(record) => {
+            // No subscribers just means no live UI; the row is still stored.
+            let _ = push_channel().send(record.clone());
+            Some(record)
+        }
+        Err(e) => {
+            tracing::warn!(
+                user_id = %params.user_id,
+                kind = params.kind.as_str(),
+                error = %e,
+                "Failed to store notification"
+            );
+            None
+        }
+    }
+}
+
+/// Store a notification for a person and also send it to their chosen
+/// delivery channel, if any.
+pub async fn notify_user(
+    store: &dyn Database,
+    params: NewNotificationParams,
+) -> Option<NotificationRecord> {
+    let record = notify(store, params).await?;
+    if let Some(preference) = delivery_preference(store, &record.user_id).await {
+        let message = if record.body.is_empty() {
+            record.title.clone()
+        } else {
+            format!("{}\n\n{}", record.title, record.body)
+        };
+        // Without a running agent there is nobody to deliver it; the inbox
+        // row still stands.
+        let _ = delivery_channel().send(ChannelDelivery {
+            channel: preference.channel,
+            recipient: preference
+                .recipient
+                .unwrap_or_else(|| record.user_id.clone()),
+            message,
+        });
+    }
+    Some(record)
+}
@@ -0,0 +1,184 @@
+//! Prometheus metrics via the `metrics` crate.
+//!
+//! Instrumented code calls the `record_*` / `set_*` helpers below, which go
+//! through the global `metrics` recorder. [`install_prometheus_recorder`]
+//! installs a Prometheus recorder at startup; until then (and in tests that
+//! don't install one) the helpers are no-ops. The gateway serves
+//! [`render`] at `GET /metrics`.
+//!
+//! Gauges that mirror state held elsewhere (queue depths, DB pool) are
+//! refreshed by the `/metrics` handler right before rendering.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use metrics::{counter, gauge, histogram};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+
+use crate::db::PoolStats;
+
+pub const HTTP_REQUESTS_TOTAL: &str = "clawyer_http_requests_total";
+pub const HTTP_REQUEST_DURATION_SECONDS: &str = "clawyer_http_request_duration_seconds";
+pub const LLM_TOKENS_TOTAL: &str = "clawyer_llm_tokens_total";
+pub const LLM_COST_USD: &str = "clawyer_llm_cost_usd";
+pub const ROUTINE_RUNS_TOTAL: &str = "clawyer_routine_runs_total";
+pub const JOBS_RUNNING: &str = "clawyer_jobs_running";
+pub const SANDBOX_JOBS: &str = "clawyer_sandbox_jobs";
+pub const SANDBOX_JOBS_FINISHED_TOTAL: &str = "clawyer_sandbox_jobs_finished_total";
+pub const INTAKE_QUEUE_DEPTH: &str = "clawyer_intake_queue_depth";
+pub const DB_POOL_CONNECTIONS: &str = "clawyer_db_pool_connections";
+pub const CHANNEL_DELIVERY_FAILURES_TOTAL: &str = "clawyer_channel_delivery_failures_total";
+
+/// Latency buckets (seconds) for request histograms: 5ms to 2 minutes, since
+/// chat and document routes can wait on an LLM.
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0,
+];
+
+static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+fn builder() -> PrometheusBuilder {
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Full(HTTP_REQUEST_DURATION_SECONDS.to_string()),
+            LATENCY_BUCKETS,
+        )
+        // Only fails for an empty bucket list; fall back to summaries.
+        .unwrap_or_else(|_| PrometheusBuilder::new())
+}
+
+/// Install the global Prometheus recorder. Safe to call more than once; only
+/// the first call installs. Returns `None` if another recorder was already
+/// installed by someone else.
+pub fn install_prometheus_recorder() -> Option<&'static PrometheusHandle> {
+    if let Some(handle) = HANDLE.get() {
+        return Some(handle);
+    }
+    match builder().install_recorder() {
+        Ok(handle) => Some(HANDLE.get_or_init(|| handle)),
+        Err(e) => {
+            tracing::warn!("Failed to install Prometheus metrics recorder: {}", e);
+            None
+        }
+    }
+}
+
+/// Prometheus text exposition of all metrics, or `None` when metrics are
+/// disabled.
+pub fn render() -> Option<String> {
+    HANDLE.get().map(PrometheusHandle::render)
+}
+
+/// One HTTP request handled by the gateway. `route` is the matched route
+/// template (e.g. `/api/matters/{id}`), never the raw path.
+pub fn record_http_request(method: &str, route: &str, status: u16, elapsed: Duration) {
+    let labels = [
+        ("method", method.to_string()),
+        ("route", route.to_string()),
+        ("status", status.to_string()),
+    ];
+    counter!(HTTP_REQUESTS_TOTAL, &labels).increment(1);
+    histogram!(
+        HTTP_REQUEST_DURATION_SECONDS,
+        "method" => method.to_string(),
+        "route" => route.to_string()
+    )
+    .record(elapsed.as_secs_f64());
+}
+
+/// Tokens and cost of one completed LLM call.
+pub fn record_llm_call(model: &str, input_tokens: u32, output_tokens: u32, cost_usd: f64) {
+    counter!(LLM_TOKENS_TOTAL, "model" => model.to_string(), "direction" => "input")
+        .increment(u64::from(input_tokens));
+    counter!(LLM_TOKENS_TOTAL, "model" => model.to_string(), "direction" => "output")
+        .increment(u64::from(output_tokens));
+    // Counters are integral; the running dollar total is a gauge that only
+    // ever goes up.
+    gauge!(LLM_COST_USD, "model" => model.to_string()).increment(cost_usd);
+}
+
+/// A routine run finished with `status` (`ok`, `attention`, `failed`).
+pub fn record_routine_run(status: &str) {
+    counter!(ROUTINE_RUNS_TOTAL, "status" => status.to_string()).increment(1);
+}
+
+/// In-process agent jobs currently held by the scheduler.
+pub fn set_jobs_running(count: usize) {
+    gauge!(JOBS_RUNNING).set(count as f64);
+}
+
+/// Sandbox container jobs holding a slot and waiting for one.
+pub fn set_sandbox_jobs(active: usize, queued: usize) {
+    gauge!(SANDBOX_JOBS, "state" => "active").set(active as f64);
+    gauge!(SANDBOX_JOBS, "state" => "queued").set(queued as f64);
+}
+
+/// A sandbox job reached a terminal state (`completed`, `failed`, ...).
+pub fn record_sandbox_job_finished(outcome: &str) {
+    counter!(SANDBOX_JOBS_FINISHED_TOTAL, "outcome" => outcome.to_string()).increment(1);
+}
+
+/// Messages waiting in a channel's agent-loop intake queue.
+pub fn set_intake_queue_depth(channel: &str, depth: usize) {
+    gauge!(INTAKE_QUEUE_DEPTH, "channel" => channel.to_string()).set(depth as f64);
+}
+
+/// Database connection pool occupancy.
+pub fn set_db_pool(stats: &PoolStats) {
+    gauge!(DB_POOL_CONNECTIONS, "state" => "max").set(stats.max_size as f64);
+    gauge!(DB_POOL_CONNECTIONS, "state" => "open").set(stats.size as f64);
+    gauge!(DB_POOL_CONNECTIONS, "state" => "idle").set(stats.available as f64);
+    gauge!(DB_POOL_CONNECTIONS, "state" => "waiting").set(stats.waiting as f64);
+}
+
+/// A response or broadcast could not be delivered on `channel`.
+pub fn record_channel_delivery_failure(channel: &str, kind: &'static str) {
+    counter!(
+        CHANNEL_DELIVERY_FAILURES_TOTAL,
+        "channel" => channel.to_string(),
+        "kind" => kind
+    )
+    .increment(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn helpers_render_prometheus_series() {
+        let recorder = builder().build_recorder();
+        let handle = recorder.handle();
+        metrics::with_local_recorder(&recorder, || {
+            record_http_request("GET", "/api/matters/{id}", 200, Duration::from_millis(30));
+            record_llm_call("gpt-test", 100, 20, 0.25);
+            record_llm_call("gpt-test", 50, 5, 0.5);
+            record_routine_run("ok");
+            record_routine_run("failed");
+            set_sandbox_jobs(2, 3);
+            set_db_pool(&PoolStats {
+                max_size: 10,
+                size: 4,
+                available: 1,
+                waiting: 0,
+            });
+            record_channel_delivery_failure("telegram", "respond");
+        });
+        let text = handle.render();
+
+        assert!(text.contains(
+            r#"clawyer_http_requests_total{method="GET",route="/api/matters/{id}",status="200"} 1"#
+        ));
+        assert!(text.contains(r#"clawyer_http_request_duration_seconds_bucket{method="GET",route="/api/matters/{id}",le="0.05"} 1"#));
+        assert!(
+            text.contains(r#"clawyer_llm_tokens_total{model="gpt-test",direction="input"} 150"#)
+        );
+        assert!(text.contains(r#"clawyer_llm_cost_usd{model="gpt-test"} 0.75"#));
+        assert!(text.contains(r#"clawyer_routine_runs_total{status="failed"} 1"#));
+        assert!(text.contains(r#"clawyer_sandbox_jobs{state="queued"} 3"#));
+        assert!(text.contains(r#"clawyer_db_pool_connections{state="open"} 4"#));
+        assert!(text.contains(
+            r#"clawyer_channel_delivery_failures_total{channel="telegram",kind="respond"} 1"#
+        ));
+    }
+}
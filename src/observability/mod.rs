@@ -9,10 +9,14 @@
 //! | `multi` | Fan-out to multiple backends simultaneously |
 //!
 //! The [`create_observer`] factory builds the right backend from
-//! [`ObservabilityConfig`]. Future backends (OpenTelemetry, Prometheus)
-//! can be added by implementing [`Observer`].
+//! [`ObservabilityConfig`]. Future backends (OpenTelemetry) can be added by
+//! implementing [`Observer`].
+//!
+//! Prometheus metrics live in [`metrics`], recorded through the `metrics`
+//! crate and served by the gateway at `GET /metrics`.
 
 mod log;
+pub mod metrics;
 mod multi;
 mod noop;
 pub mod traits;
@@ -27,12 +31,15 @@ pub use self::traits::{Observer, ObserverEvent, ObserverMetric};
 pub struct ObservabilityConfig {
     /// Backend name: "none", "noop", "log".
     pub backend: String,
+    /// Install the Prometheus recorder and serve `GET /metrics`.
+    pub metrics_enabled: bool,
 }
 
 impl Default for ObservabilityConfig {
     fn default() -> Self {
         Self {
             backend: "none".into(),
+            metrics_enabled: true,
         }
     }
 }
@@ -62,6 +69,7 @@ mod tests {
     fn factory_returns_noop_for_none() {
         let cfg = ObservabilityConfig {
             backend: "none".into(),
+            ..Default::default()
         };
         let obs = create_observer(&cfg);
         assert_eq!(obs.name(), "noop");
@@ -71,6 +79,7 @@ mod tests {
     fn factory_returns_noop_for_empty() {
         let cfg = ObservabilityConfig {
             backend: String::new(),
+            ..Default::default()
         };
         let obs = create_observer(&cfg);
         assert_eq!(obs.name(), "noop");
@@ -80,6 +89,7 @@ mod tests {
     fn factory_returns_noop_for_unknown() {
         let cfg = ObservabilityConfig {
             backend: "prometheus".into(),
+            ..Default::default()
         };
         let obs = create_observer(&cfg);
         assert_eq!(obs.name(), "noop");
@@ -89,6 +99,7 @@ mod tests {
     fn factory_returns_log_for_log() {
         let cfg = ObservabilityConfig {
             backend: "log".into(),
+            ..Default::default()
         };
         let obs = create_observer(&cfg);
         assert_eq!(obs.name(), "log");
@@ -98,6 +109,7 @@ mod tests {
     fn factory_returns_noop_for_noop() {
         let cfg = ObservabilityConfig {
             backend: "noop".into(),
+            ..Default::default()
         };
         let obs = create_observer(&cfg);
         assert_eq!(obs.name(), "noop");
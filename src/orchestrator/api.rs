@@ -14,6 +14,7 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::{Mutex, broadcast};
 use uuid::Uuid;
 
+use crate::agent::cost_guard::{CostAttribution, CostGuard};
 use crate::channels::web::types::SseEvent;
 use crate::db::Database;
 use crate::llm::{CompletionRequest, LlmProvider, ToolCompletionRequest};
@@ -78,6 +79,8 @@ pub struct OrchestratorState {
     pub secrets_store: Option<Arc<dyn SecretsStore + Send + Sync>>,
     /// User ID for secret lookups (single-tenant, typically "default").
     pub user_id: String,
+    /// Records sandbox LLM spend against the job owner's active matter.
+    pub cost_guard: Option<Arc<CostGuard>>,
 }
 
 /// The orchestrator's internal API server.
@@ -183,6 +186,7 @@ async fn llm_complete(
         tracing::error!("LLM completion failed for job {}: {}", job_id, e);
         StatusCode::BAD_GATEWAY
     })?;
+    record_sandbox_llm_usage(&state, job_id, resp.input_tokens, resp.output_tokens).await;
 
     Ok(Json(ProxyCompletionResponse {
         content: resp.content,
@@ -211,6 +215,7 @@ async fn llm_complete_with_tools(
         tracing::error!("LLM tool completion failed for job {}: {}", job_id, e);
         StatusCode::BAD_GATEWAY
     })?;
+    record_sandbox_llm_usage(&state, job_id, resp.input_tokens, resp.output_tokens).await;
 
     Ok(Json(ProxyToolCompletionResponse {
         content: resp.content,
@@ -221,6 +226,37 @@ async fn llm_complete_with_tools(
     }))
 }
 
+/// Bill a proxied LLM call to the sandbox job's owner and their active matter.
+async fn record_sandbox_llm_usage(
+    state: &OrchestratorState,
+    job_id: Uuid,
+    input_tokens: u32,
+    output_tokens: u32,
+) {
+    let Some(cost_guard) = state.cost_guard.as_ref() else {
+        return;
+    };
+    let mut user_id = state.user_id.clone();
+    let mut matter_id = None;
+    if let Some(store) = state.store.as_ref() {
+        if let Ok(Some(job)) = store.get_sandbox_job(job_id).await {
+            user_id = job.user_id;
+        }
+        matter_id =
+            crate::agent::cost_guard::active_matter_for_user(store.as_ref(), &user_id).await;
+    }
+    let attribution = CostAttribution::new(user_id, matter_id).sandbox_job(job_id);
+    cost_guard
+        .record_llm_call(
+            &state.llm.active_model_name(),
+            input_tokens,
+            output_tokens,
+            Some(state.llm.cost_per_token()),
+            Some(&attribution),
+        )
+        .await;
+}
+
 async fn report_status(
     State(state): State<OrchestratorState>,
     Path(job_id): Path<Uuid>,
@@ -499,6 +535,7 @@ mod tests {
             store: None,
             secrets_store: None,
             user_id: "default".to_string(),
+            cost_guard: None,
         }
     }
 
@@ -787,6 +824,7 @@ mod tests {
             store: None,
             secrets_store: Some(secrets_store),
             user_id: "default".to_string(),
+            cost_guard: None,
         };
 
         let router = OrchestratorApi::router(state);
@@ -822,6 +860,7 @@ mod tests {
             store: None,
             secrets_store: None,
             user_id: "default".to_string(),
+            cost_guard: None,
         };
 
         let job_id = Uuid::new_v4();
@@ -877,6 +916,7 @@ mod tests {
             store: None,
             secrets_store: None,
             user_id: "default".to_string(),
+            cost_guard: None,
         };
 
         let job_id = Uuid::new_v4();
@@ -925,6 +965,7 @@ mod tests {
             store: None,
             secrets_store: None,
             user_id: "default".to_string(),
+            cost_guard: None,
         };
 
         let job_id = Uuid::new_v4();
@@ -970,7 +1011,14 @@ mod tests {
                     container_id: "test-container".to_string(),
                     state: crate::orchestrator::job_manager::ContainerState::Running,
                     mode: crate::orchestrator::job_manager::JobMode::Worker,
+                    user_id: "default".to_string(),
+                    priority: crate::orchestrator::job_manager::JobPriority::Normal,
                     created_at: chrono::Utc::now(),
+                    started_at: None,
+                    queued_at: None,
+                    preemptions: 0,
+                    attempt: 1,
+                    retry_at: None,
                     project_dir: None,
                     task_description: "test".to_string(),
                     last_worker_status: None,
@@ -0,0 +1,230 @@
+//! Job artifacts: files a sandbox job leaves in its project directory.
+//!
+//! When a job finishes, the files in its bind-mounted project directory are
+//! copied to `~/.clawyer/artifacts/<job id>/` and registered in the database
+//! with their size and content type, so they stay downloadable after the
+//! project directory is reused or cleaned up. A background sweep deletes
+//! artifacts older than the configured retention window.
+
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::db::{Database, JobArtifactRecord};
+use crate::error::DatabaseError;
+
+/// Most files registered per job.
+pub const MAX_ARTIFACTS_PER_JOB: usize = 200;
+/// Largest single file registered as an artifact.
+pub const MAX_ARTIFACT_BYTES: u64 = 25 * 1024 * 1024;
+/// How often the retention sweep runs.
+const GC_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Host directory holding stored artifact copies.
+pub fn artifacts_root() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".clawyer")
+        .join("artifacts")
+}
+
+/// Content type for an artifact, guessed from its extension.
+pub fn content_type_for(name: &str) -> String {
+    mime_guess::from_path(name)
+        .first_or_octet_stream()
+        .essence_str()
+        .to_string()
+}
+
+/// Normalize a client-supplied artifact name (`reports/summary.md`).
+///
+/// Returns `None` for empty, absolute, or parent-relative names.
+pub fn clean_artifact_name(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if raw.is_empty() || raw.contains('\\') {
+        return None;
+    }
+    let mut parts = Vec::new();
+    for component in Path::new(raw).components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str()?.to_string()),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// Copy a finished job's files into `dest` and describe them.
+///
+/// Hidden entries (`.git`, `.env`, ...) and symlinks are skipped, as are files
+/// over [`MAX_ARTIFACT_BYTES`]; at most [`MAX_ARTIFACTS_PER_JOB`] files are
+/// taken, in name order.
+pub async fn collect_artifacts(
+    job_id: Uuid,
+    project_dir: &Path,
+    dest: &Path,
+) -> std::io::Result<Vec<JobArtifactRecord>> {
+    let mut files = Vec::new();
+    let mut pending = vec![(project_dir.to_path_buf(), String::new())];
+    while let Some((dir, prefix)) = pending.pop() {
+        let mut entries = tokio::fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let Ok(file_name) = entry.file_name().into_string() else {
+                continue;
+            };
+            if file_name.starts_with('.') {
+                continue;
+            }
+            let name = if prefix.is_empty() {
+                file_name
+            } else {
+                format!("{prefix}/{file_name}")
+            };
+            let metadata = tokio::fs::symlink_metadata(entry.path()).await?;
+            if metadata.is_dir() {
+                pending.push((entry.path(), name));
+            } else if metadata.is_file() && metadata.len() <= MAX_ARTIFACT_BYTES {
+                files.push((name, entry.path(), metadata.len()));
+            }
+        }
+    }
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    files.truncate(MAX_ARTIFACTS_PER_JOB);
+
+    let now = Utc::now();
+    let mut artifacts = Vec::with_capacity(files.len());
+    for (name, source, size) in files {
+        let target = dest.join(&name);
+        if let Some(parent) = target.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::copy(&source, &target).await?;
+        artifacts.push(JobArtifactRecord {
+            job_id,
+            content_type: content_type_for(&name),
+            name,
+            size_bytes: size as i64,
+            storage_path: target.to_string_lossy().into_owned(),
+            promoted_to: None,
+            created_at: now,
+        });
+    }
+    Ok(artifacts)
+}
+
+/// Copy and register the artifacts of a finished job. Returns how many were
+/// registered.
+pub async fn register_job_artifacts(
+    store: &dyn Database,
+    job_id: Uuid,
+    project_dir: &Path,
+    root: &Path,
+) -> Result<usize, String> {
+    let dest = root.join(job_id.to_string());
+    let artifacts = collect_artifacts(job_id, project_dir, &dest)
+        .await
+        .map_err(|e| format!("failed to copy artifacts: {e}"))?;
+    for artifact in &artifacts {
+        store
+            .save_job_artifact(artifact)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(artifacts.len())
+}
+
+/// Delete artifacts registered more than `retention` ago, along with their
+/// stored copies. Returns how many were removed.
+pub async fn purge_expired_artifacts(
+    store: &dyn Database,
+    retention: Duration,
+) -> Result<usize, DatabaseError> {
+    let retention = chrono::Duration::from_std(retention).unwrap_or(chrono::Duration::MAX);
+    let Some(cutoff) = Utc::now().checked_sub_signed(retention) else {
+        return Ok(0);
+    };
+    let expired = store.delete_job_artifacts_before(cutoff).await?;
+    for artifact in &expired {
+        let path = Path::new(&artifact.storage_path);
+        if let Err(e) = tokio::fs::remove_file(path).await
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!(
+                job_id = %artifact.job_id,
+                path = %path.display(),
+                error = %e,
+                "Failed to remove expired artifact"
+            );
+        }
+    }
+    Ok(expired.len())
+}
+
+/// Run [`purge_expired_artifacts`] hourly. A zero retention keeps artifacts
+/// forever and starts no task.
+pub fn spawn_artifact_gc(
+    store: Arc<dyn Database>,
+    retention_days: u32,
+) -> Option<tokio::task::JoinHandle<()>> {
+    if retention_days == 0 {
+        return None;
+    }
+    let retention = Duration::from_secs(u64::from(retention_days) * 24 * 60 * 60);
+    Some(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(GC_INTERVAL);
+        loop {
+            interval.tick().await;
+            match purge_expired_artifacts(store.as_ref(), retention).await {
+                Ok(0) => {}
+                Ok(n) => tracing::info!(removed = n, "Purged expired job artifacts"),
+                Err(e) => tracing::warn!(error = %e, "Job artifact retention sweep failed"),
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn artifact_names_stay_relative() {
+        assert_eq!(
+            clean_artifact_name("./reports//summary.md").as_deref(),
+            Some("reports/summary.md")
+        );
+        assert_eq!(clean_artifact_name("../secrets.txt"), None);
+        assert_eq!(clean_artifact_name("/etc/passwd"), None);
+        assert_eq!(clean_artifact_name("a\\b"), None);
+        assert_eq!(clean_artifact_name("  "), None);
+    }
+
+    #[tokio::test]
+    async fn collects_visible_files_with_content_types() {
+        let project = tempfile::tempdir().unwrap();
+        let dest = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(project.path().join("out")).unwrap();
+        std::fs::create_dir_all(project.path().join(".git")).unwrap();
+        std::fs::write(project.path().join("out/summary.md"), "# Summary").unwrap();
+        std::fs::write(project.path().join("damages.csv"), "a,b\n1,2\n").unwrap();
+        std::fs::write(project.path().join(".env"), "SECRET=1").unwrap();
+        std::fs::write(project.path().join(".git/HEAD"), "ref").unwrap();
+
+        let job_id = Uuid::new_v4();
+        let artifacts = collect_artifacts(job_id, project.path(), dest.path())
+            .await
+            .unwrap();
+        let names: Vec<&str> = artifacts.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["damages.csv", "out/summary.md"]);
+        assert_eq!(artifacts[0].content_type, "text/csv");
+        assert_eq!(artifacts[1].size_bytes, 9);
+        assert_eq!(
+            std::fs::read_to_string(dest.path().join("out/summary.md")).unwrap(),
+            "# Summary"
+        );
+    }
+}
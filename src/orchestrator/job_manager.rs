@@ -2,18 +2,47 @@
 //!
 //! Extends the existing `SandboxManager` infrastructure to support persistent
 //! containers with their own agent loops (as opposed to ephemeral per-command containers).
+//!
+//! Jobs are admitted through a priority queue: at most
+//! `max_concurrent_jobs` containers run at once (and `max_jobs_per_user` per
+//! user); the rest wait as [`ContainerState::Queued`] and start, highest
+//! priority first, as slots free up. An interactive job that finds no free
+//! slot preempts the most recently started routine job, which goes back to
+//! the queue and restarts from scratch later.
+//!
+//! Failed attempts go through the job mode's [`RetryPolicy`]: transient
+//! failures are requeued with exponential backoff, and jobs that exhaust
+//! their attempts are recorded as `dead_letter` for manual requeue.
+//!
+//! Routine jobs only start inside the owner's background execution windows
+//! and never on blackout dates (see [`crate::agent::execution_window`]);
+//! until then they stay queued and the queue rechecks them periodically.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
+use futures::StreamExt;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+use crate::agent::execution_window::load_policy;
+use crate::db::{AuditSeverity, Database, NewNotificationParams, NotificationKind};
 use crate::error::OrchestratorError;
 use crate::orchestrator::auth::{CredentialGrant, TokenStore};
-use crate::sandbox::connect_docker;
+use crate::orchestrator::retry::{FailureClass, RetryPolicy, classify_failure};
+use crate::sandbox::{ContainerOutput, connect_docker};
+
+/// Container output lines persisted per attempt before tailing stops.
+const MAX_LOG_LINES_PER_ATTEMPT: usize = 5_000;
+/// Longest single log line kept; longer lines are cut.
+const MAX_LOG_LINE_CHARS: usize = 4_096;
+
+/// How often routine jobs held back by the execution policy are rechecked.
+const EXECUTION_WINDOW_RECHECK: Duration = Duration::from_secs(5 * 60);
 
 /// Which mode a sandbox container runs in.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,6 +68,43 @@ impl std::fmt::Display for JobMode {
     }
 }
 
+/// Scheduling priority of a sandbox job. Higher priorities start first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum JobPriority {
+    /// Background work started by a routine; preemptible.
+    Routine,
+    /// Jobs started by agent-scheduled work or the API.
+    #[default]
+    Normal,
+    /// Jobs started from a chat turn; preempt routine jobs when full.
+    Interactive,
+}
+
+impl JobPriority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Routine => "routine",
+            Self::Normal => "normal",
+            Self::Interactive => "interactive",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "routine" => Some(Self::Routine),
+            "normal" => Some(Self::Normal),
+            "interactive" => Some(Self::Interactive),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for JobPriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// Configuration for the container job manager.
 #[derive(Debug, Clone)]
 pub struct ContainerJobConfig {
@@ -65,6 +131,24 @@ pub struct ContainerJobConfig {
     pub claude_code_memory_limit_mb: u64,
     /// Allowed tool patterns for Claude Code (passed as CLAUDE_CODE_ALLOWED_TOOLS env var).
     pub claude_code_allowed_tools: Vec<String>,
+    /// Containers allowed to run at once; further jobs are queued.
+    pub max_concurrent_jobs: usize,
+    /// Containers a single user may run at once.
+    pub max_jobs_per_user: usize,
+    /// Retry policy for worker-mode jobs.
+    pub worker_retry: RetryPolicy,
+    /// Retry policy for Claude Code jobs.
+    pub claude_code_retry: RetryPolicy,
+}
+
+impl ContainerJobConfig {
+    /// Retry policy for a job mode.
+    pub fn retry_policy(&self, mode: JobMode) -> &RetryPolicy {
+        match mode {
+            JobMode::Worker => &self.worker_retry,
+            JobMode::ClaudeCode => &self.claude_code_retry,
+        }
+    }
 }
 
 impl Default for ContainerJobConfig {
@@ -80,6 +164,10 @@ impl Default for ContainerJobConfig {
             claude_code_max_turns: 50,
             claude_code_memory_limit_mb: 4096,
             claude_code_allowed_tools: crate::config::ClaudeCodeConfig::default().allowed_tools,
+            max_concurrent_jobs: 4,
+            max_jobs_per_user: 2,
+            worker_retry: RetryPolicy::default(),
+            claude_code_retry: crate::config::ClaudeCodeConfig::default().retry,
         }
     }
 }
@@ -87,6 +175,8 @@ impl Default for ContainerJobConfig {
 /// State of a container.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ContainerState {
+    /// Waiting for a free slot (never started, or preempted).
+    Queued,
     Creating,
     Running,
     Stopped,
@@ -96,6 +186,7 @@ pub enum ContainerState {
 impl std::fmt::Display for ContainerState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Self::Queued => write!(f, "queued"),
             Self::Creating => write!(f, "creating"),
             Self::Running => write!(f, "running"),
             Self::Stopped => write!(f, "stopped"),
@@ -111,7 +202,20 @@ pub struct ContainerHandle {
     pub container_id: String,
     pub state: ContainerState,
     pub mode: JobMode,
+    /// User the job runs for (per-user concurrency limit).
+    pub user_id: String,
+    pub priority: JobPriority,
     pub created_at: DateTime<Utc>,
+    /// When the current container was started; `None` while queued.
+    pub started_at: Option<DateTime<Utc>>,
+    /// When the job last entered the queue; `None` once started.
+    pub queued_at: Option<DateTime<Utc>>,
+    /// Times the job was stopped to make room for an interactive job.
+    pub preemptions: u32,
+    /// 1-based attempt number; bumped each time a failure is retried.
+    pub attempt: u32,
+    /// Earliest start of the next attempt while waiting out a retry backoff.
+    pub retry_at: Option<DateTime<Utc>>,
     pub project_dir: Option<PathBuf>,
     pub task_description: String,
     /// Last status message reported by the worker (iteration count, progress, etc.).
@@ -124,6 +228,166 @@ pub struct ContainerHandle {
     // It lives only in the TokenStore (never logged, serialized, or persisted).
 }
 
+impl ContainerHandle {
+    /// Whether the job currently occupies a container slot.
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self.state,
+            ContainerState::Creating | ContainerState::Running
+        )
+    }
+}
+
+/// Outcome of submitting a job to [`ContainerJobManager::create_job`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobAdmission {
+    /// The container was created and started.
+    Started,
+    /// All slots are taken; the job waits at this 1-based queue position.
+    Queued { position: usize },
+    /// The container failed to start; the job is requeued for another attempt.
+    Retrying {
+        attempt: u32,
+        retry_at: DateTime<Utc>,
+    },
+}
+
+/// What became of a failed attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureOutcome {
+    /// Requeued; the next attempt starts no earlier than `retry_at`.
+    Retry {
+        attempt: u32,
+        retry_at: DateTime<Utc>,
+    },
+    /// The failure is not retried.
+    Failed,
+    /// The failure was retryable but every attempt is used up.
+    DeadLetter,
+}
+
+/// Apply a retry policy to a failed attempt, requeueing the handle on retry.
+fn settle_failure(
+    handle: &mut ContainerHandle,
+    policy: &RetryPolicy,
+    class: FailureClass,
+    now: DateTime<Utc>,
+) -> FailureOutcome {
+    if !policy.retries(class) {
+        return FailureOutcome::Failed;
+    }
+    if handle.attempt >= policy.max_attempts {
+        return FailureOutcome::DeadLetter;
+    }
+    let delay = chrono::Duration::from_std(policy.backoff(handle.attempt)).unwrap_or_default();
+    handle.attempt += 1;
+    handle.state = ContainerState::Queued;
+    handle.container_id.clear();
+    handle.started_at = None;
+    handle.queued_at = Some(now);
+    handle.retry_at = Some(now + delay);
+    handle.completion_result = None;
+    FailureOutcome::Retry {
+        attempt: handle.attempt,
+        retry_at: now + delay,
+    }
+}
+
+/// Failure reason recorded for a dead-lettered job.
+fn dead_letter_reason(message: &str, attempts: u32) -> String {
+    format!("{} (gave up after {} attempts)", message, attempts)
+}
+
+/// Tell a dead-lettered job's owner through their notification inbox.
+async fn notify_dead_letter(store: &dyn Database, job_id: Uuid, reason: Option<&str>) {
+    let job = match store.get_sandbox_job(job_id).await {
+        Ok(Some(job)) => job,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!(job_id = %job_id, error = %e, "Failed to load dead-lettered job");
+            return;
+        }
+    };
+    let task: String = job.task.chars().take(80).collect();
+    crate::notifications::notify(
+        store,
+        NewNotificationParams {
+            user_id: job.user_id,
+            kind: NotificationKind::System,
+            severity: AuditSeverity::Warn,
+            title: format!("Sandbox job needs requeue: {}", task),
+            body: reason.unwrap_or_default().to_string(),
+            matter_id: None,
+            source: Some(format!("job:{}", job_id)),
+        },
+    )
+    .await;
+}
+
+/// Point-in-time view of the job queue.
+#[derive(Debug, Clone)]
+pub struct JobQueueSnapshot {
+    pub max_concurrent_jobs: usize,
+    pub max_jobs_per_user: usize,
+    /// Jobs holding a slot, oldest first.
+    pub active: Vec<ContainerHandle>,
+    /// Waiting jobs in the order they will start.
+    pub queued: Vec<ContainerHandle>,
+}
+
+/// What to do with a newly submitted job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Admission {
+    Start,
+    /// Stop this routine job, then start.
+    Preempt(Uuid),
+    Wait,
+}
+
+/// Decide whether a job for `user_id` at `priority` can start now.
+fn plan_admission(
+    handles: &HashMap<Uuid, ContainerHandle>,
+    config: &ContainerJobConfig,
+    user_id: &str,
+    priority: JobPriority,
+) -> Admission {
+    let active: Vec<&ContainerHandle> = handles.values().filter(|h| h.is_active()).collect();
+    let user_full =
+        active.iter().filter(|h| h.user_id == user_id).count() >= config.max_jobs_per_user.max(1);
+    let global_full = active.len() >= config.max_concurrent_jobs.max(1);
+    if !user_full && !global_full {
+        return Admission::Start;
+    }
+    if priority != JobPriority::Interactive {
+        return Admission::Wait;
+    }
+    // Preempting the caller's own routine job frees both limits; anyone's
+    // frees the global one. Prefer the job that has run the least.
+    active
+        .into_iter()
+        .filter(|h| {
+            h.state == ContainerState::Running
+                && h.priority == JobPriority::Routine
+                && (!user_full || h.user_id == user_id)
+        })
+        .max_by_key(|h| h.started_at)
+        .map_or(Admission::Wait, |h| Admission::Preempt(h.job_id))
+}
+
+/// Queued jobs in start order: highest priority first, then oldest.
+fn queue_order(handles: &HashMap<Uuid, ContainerHandle>) -> Vec<&ContainerHandle> {
+    let mut queued: Vec<&ContainerHandle> = handles
+        .values()
+        .filter(|h| h.state == ContainerState::Queued)
+        .collect();
+    queued.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then(a.created_at.cmp(&b.created_at))
+    });
+    queued
+}
+
 /// Result reported by a worker on completion.
 #[derive(Debug, Clone)]
 pub struct CompletionResult {
@@ -131,6 +395,31 @@ pub struct CompletionResult {
     pub message: Option<String>,
 }
 
+/// A short, non-interactive script run in a throwaway container.
+///
+/// Unlike worker jobs there is no agent loop, no orchestrator token and no
+/// network: the container sees `script_dir` and `input_dir` read-only, may
+/// only write to `output_dir`, and is removed as soon as it exits or the
+/// timeout fires. All three directories must live under
+/// `~/.clawyer/projects/`.
+#[derive(Debug, Clone)]
+pub struct ScriptRun {
+    /// Command executed with `/workspace` as the working directory.
+    pub command: Vec<String>,
+    /// Mounted read-only at `/workspace/script`.
+    pub script_dir: PathBuf,
+    /// Mounted read-only at `/workspace/inputs`.
+    pub input_dir: PathBuf,
+    /// Mounted read-write at `/workspace/outputs`.
+    pub output_dir: PathBuf,
+    /// Memory limit in MB (capped at the configured worker limit).
+    pub memory_limit_mb: u64,
+    /// Wall-clock limit for the whole run.
+    pub timeout: Duration,
+    /// Combined stdout/stderr bytes kept.
+    pub max_output_bytes: usize,
+}
+
 /// Validate that a project directory is under `~/.clawyer/projects/`.
 ///
 /// Returns the canonicalized path if valid. Creates the base directory if
@@ -203,12 +492,17 @@ fn validate_bind_mount_path(
 }
 
 /// Manages the lifecycle of Docker containers for sandboxed job execution.
+#[derive(Clone)]
 pub struct ContainerJobManager {
     config: ContainerJobConfig,
     token_store: TokenStore,
     pub(crate) containers: Arc<RwLock<HashMap<Uuid, ContainerHandle>>>,
     /// Cached Docker connection (created on first use).
     docker: Arc<RwLock<Option<bollard::Docker>>>,
+    /// Records status changes of jobs started or preempted by the queue.
+    store: Option<Arc<dyn Database>>,
+    /// Set while a recheck of held routine jobs is scheduled.
+    window_recheck_pending: Arc<AtomicBool>,
 }
 
 impl ContainerJobManager {
@@ -218,9 +512,17 @@ impl ContainerJobManager {
             token_store,
             containers: Arc::new(RwLock::new(HashMap::new())),
             docker: Arc::new(RwLock::new(None)),
+            store: None,
+            window_recheck_pending: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Persist queue-driven status changes (`queued` -> `running`) to the job store.
+    pub fn with_store(mut self, store: Option<Arc<dyn Database>>) -> Self {
+        self.store = store;
+        self
+    }
+
     /// Get or create a Docker connection.
     async fn docker(&self) -> Result<bollard::Docker, OrchestratorError> {
         {
@@ -238,56 +540,344 @@ impl ContainerJobManager {
         Ok(docker)
     }
 
-    /// Create and start a new container for a job.
+    /// Submit a job and start its container if a slot is free.
     ///
     /// The caller provides the `job_id` so it can be persisted to the database
     /// before the container is created. Credential grants are stored in the
-    /// TokenStore and served on-demand via the `/credentials` endpoint.
-    /// Returns the auth token for the worker.
+    /// TokenStore and served on-demand via the `/credentials` endpoint. When
+    /// every slot is taken the job is queued (or, for interactive jobs, a
+    /// routine job is preempted) and started later by the queue; the auth
+    /// token is issued when the container actually starts.
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_job(
         &self,
         job_id: Uuid,
+        user_id: &str,
         task: &str,
         project_dir: Option<PathBuf>,
         mode: JobMode,
+        priority: JobPriority,
         credential_grants: Vec<CredentialGrant>,
-    ) -> Result<String, OrchestratorError> {
-        // Generate auth token (stored in TokenStore, never logged)
-        let token = self.token_store.create_token(job_id).await;
-
+    ) -> Result<JobAdmission, OrchestratorError> {
         // Store credential grants (revoked automatically when the token is revoked)
         self.token_store
             .store_grants(job_id, credential_grants)
             .await;
 
-        // Record the handle
-        let handle = ContainerHandle {
+        let now = Utc::now();
+        let mut handle = ContainerHandle {
             job_id,
             container_id: String::new(), // set after container creation
-            state: ContainerState::Creating,
+            state: ContainerState::Queued,
             mode,
-            created_at: Utc::now(),
-            project_dir: project_dir.clone(),
+            user_id: user_id.to_string(),
+            priority,
+            created_at: now,
+            started_at: None,
+            queued_at: Some(now),
+            preemptions: 0,
+            attempt: 1,
+            retry_at: None,
+            project_dir,
             task_description: task.to_string(),
             last_worker_status: None,
             worker_iteration: 0,
             completion_result: None,
         };
-        self.containers.write().await.insert(job_id, handle);
 
-        // Run the actual container creation. On any failure, revoke the token
-        // and remove the handle so we don't leak resources.
-        match self
-            .create_job_inner(job_id, &token, project_dir, mode)
+        let hold = if priority == JobPriority::Routine {
+            self.background_hold(user_id).await
+        } else {
+            None
+        };
+        let admission = {
+            let mut containers = self.containers.write().await;
+            let admission = if hold.is_some() {
+                Admission::Wait
+            } else {
+                plan_admission(&containers, &self.config, user_id, priority)
+            };
+            if let Admission::Preempt(victim) = admission
+                && let Some(preempted) = containers.get_mut(&victim)
+            {
+                preempted.state = ContainerState::Queued;
+                preempted.queued_at = Some(now);
+                preempted.started_at = None;
+                preempted.preemptions += 1;
+            }
+            if admission != Admission::Wait {
+                handle.state = ContainerState::Creating;
+                handle.queued_at = None;
+                handle.started_at = Some(now);
+            }
+            containers.insert(job_id, handle);
+            admission
+        };
+
+        match admission {
+            Admission::Wait => {
+                let position = self.queue_position(job_id).await.unwrap_or(1);
+                if let Some(reason) = hold {
+                    tracing::info!(job_id = %job_id, position, %reason, "Holding routine sandbox job");
+                    self.schedule_window_recheck();
+                    return Ok(JobAdmission::Queued { position });
+                }
+                tracing::info!(job_id = %job_id, %priority, position, "Queued sandbox job");
+                return Ok(JobAdmission::Queued { position });
+            }
+            Admission::Preempt(victim) => self.preempt(victim, job_id).await,
+            Admission::Start => {}
+        }
+
+        match self.start_container(job_id).await {
+            Ok(()) => Ok(JobAdmission::Started),
+            Err(e) => match self
+                .fail_attempt(job_id, FailureClass::ContainerStart)
+                .await
+            {
+                FailureOutcome::Retry { attempt, retry_at } => {
+                    tracing::warn!(job_id = %job_id, error = %e, attempt, "Sandbox job failed to start; retrying");
+                    Ok(JobAdmission::Retrying { attempt, retry_at })
+                }
+                FailureOutcome::Failed | FailureOutcome::DeadLetter => {
+                    self.token_store.revoke(job_id).await;
+                    self.containers.write().await.remove(&job_id);
+                    self.spawn_dispatch();
+                    Err(e)
+                }
+            },
+        }
+    }
+
+    /// Apply the job mode's retry policy to a failed attempt. On retry the
+    /// handle is requeued with a backoff and the old token is replaced, so a
+    /// container that is still shutting down cannot report in. Unknown jobs
+    /// count as not retried; the caller finalizes `Failed` and `DeadLetter`.
+    async fn fail_attempt(&self, job_id: Uuid, class: FailureClass) -> FailureOutcome {
+        let outcome = {
+            let mut containers = self.containers.write().await;
+            match containers.get_mut(&job_id) {
+                Some(handle) => {
+                    let policy = self.config.retry_policy(handle.mode);
+                    settle_failure(handle, policy, class, Utc::now())
+                }
+                None => FailureOutcome::Failed,
+            }
+        };
+        if let FailureOutcome::Retry { attempt, retry_at } = outcome {
+            self.token_store.create_token(job_id).await;
+            tracing::info!(job_id = %job_id, %class, attempt, %retry_at, "Requeued sandbox job for retry");
+            self.record_status(job_id, "queued", None).await;
+            self.spawn_dispatch_after((retry_at - Utc::now()).to_std().unwrap_or_default());
+        }
+        outcome
+    }
+
+    /// Issue a token and create the container for a handle already marked
+    /// `Creating`. On failure the caller retries or revokes the token.
+    async fn start_container(&self, job_id: Uuid) -> Result<(), OrchestratorError> {
+        let (project_dir, mode) = {
+            let containers = self.containers.read().await;
+            let handle = containers
+                .get(&job_id)
+                .ok_or(OrchestratorError::ContainerNotFound { job_id })?;
+            (handle.project_dir.clone(), handle.mode)
+        };
+        // Generate auth token (stored in TokenStore, never logged). A restart
+        // after preemption replaces the previous container's token.
+        let token = self.token_store.create_token(job_id).await;
+        self.create_job_inner(job_id, &token, project_dir, mode)
             .await
-        {
-            Ok(()) => Ok(token),
-            Err(e) => {
-                self.token_store.revoke(job_id).await;
-                self.containers.write().await.remove(&job_id);
-                Err(e)
+    }
+
+    /// Stop a routine job's container so `by` can take its slot. The job keeps
+    /// its credential grants and restarts from the queue.
+    async fn preempt(&self, victim: Uuid, by: Uuid) {
+        let container_id = {
+            let mut containers = self.containers.write().await;
+            containers
+                .get_mut(&victim)
+                .map(|h| std::mem::take(&mut h.container_id))
+                .unwrap_or_default()
+        };
+        // Replacing the token locks out the old container (it may still
+        // report in while stopping) without dropping the credential grants.
+        self.token_store.create_token(victim).await;
+        if !container_id.is_empty() {
+            self.remove_container(victim, &container_id, 10).await;
+        }
+        tracing::info!(job_id = %victim, preempted_by = %by, "Preempted routine sandbox job");
+        self.record_status(victim, "queued", None).await;
+    }
+
+    /// Start queued jobs while slots are free. Jobs waiting out a retry
+    /// backoff are skipped until their `retry_at`, and routine jobs until
+    /// their owner's execution policy lets them start.
+    pub async fn dispatch_queued(&self) {
+        let held = self.held_routine_users().await;
+        if !held.is_empty() {
+            self.schedule_window_recheck();
+        }
+        loop {
+            let next = {
+                let mut containers = self.containers.write().await;
+                let now = Utc::now();
+                let next = queue_order(&containers)
+                    .into_iter()
+                    .filter(|h| h.retry_at.is_none_or(|at| at <= now))
+                    .filter(|h| h.priority != JobPriority::Routine || !held.contains(&h.user_id))
+                    .find(|h| {
+                        plan_admission(&containers, &self.config, &h.user_id, JobPriority::Normal)
+                            == Admission::Start
+                    })
+                    .map(|h| h.job_id);
+                if let Some(job_id) = next
+                    && let Some(handle) = containers.get_mut(&job_id)
+                {
+                    handle.state = ContainerState::Creating;
+                    handle.queued_at = None;
+                    handle.retry_at = None;
+                    handle.started_at = Some(now);
+                }
+                next
+            };
+            let Some(job_id) = next else {
+                return;
+            };
+            let Err(e) = self.start_container(job_id).await else {
+                self.record_status(job_id, "running", None).await;
+                continue;
+            };
+            tracing::warn!(job_id = %job_id, error = %e, "Failed to start queued sandbox job");
+            let attempts = self.get_handle(job_id).await.map_or(1, |h| h.attempt);
+            let status = match self
+                .fail_attempt(job_id, FailureClass::ContainerStart)
+                .await
+            {
+                FailureOutcome::Retry { .. } => continue,
+                FailureOutcome::Failed => ("failed", e.to_string()),
+                FailureOutcome::DeadLetter => {
+                    ("dead_letter", dead_letter_reason(&e.to_string(), attempts))
+                }
+            };
+            self.token_store.revoke(job_id).await;
+            self.containers.write().await.remove(&job_id);
+            self.record_status(job_id, status.0, Some(status.1)).await;
+        }
+    }
+
+    /// Why `user_id`'s execution policy holds routine jobs back right now, if it does.
+    async fn background_hold(&self, user_id: &str) -> Option<String> {
+        let store = self.store.as_ref()?;
+        load_policy(store.as_ref(), user_id)
+            .await
+            .background_hold(Utc::now())
+    }
+
+    /// Owners of queued routine jobs whose execution policy holds them back.
+    async fn held_routine_users(&self) -> HashSet<String> {
+        let users: HashSet<String> = self
+            .containers
+            .read()
+            .await
+            .values()
+            .filter(|h| h.state == ContainerState::Queued && h.priority == JobPriority::Routine)
+            .map(|h| h.user_id.clone())
+            .collect();
+        let mut held = HashSet::new();
+        for user_id in users {
+            if self.background_hold(&user_id).await.is_some() {
+                held.insert(user_id);
             }
         }
+        held
+    }
+
+    /// Dispatch again once held routine jobs may have entered their window.
+    fn schedule_window_recheck(&self) {
+        if self.window_recheck_pending.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let manager = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(EXECUTION_WINDOW_RECHECK).await;
+            manager
+                .window_recheck_pending
+                .store(false, Ordering::Release);
+            manager.dispatch_queued().await;
+        });
+    }
+
+    /// Run [`Self::dispatch_queued`] in the background after a slot frees up.
+    fn spawn_dispatch(&self) {
+        self.spawn_dispatch_after(Duration::ZERO);
+    }
+
+    /// Run [`Self::dispatch_queued`] in the background once `delay` has passed.
+    fn spawn_dispatch_after(&self, delay: Duration) {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            manager.dispatch_queued().await;
+        });
+    }
+
+    /// Persist a queue-driven status change, if a store is attached.
+    async fn record_status(&self, job_id: Uuid, status: &str, failure: Option<String>) {
+        let Some(store) = self.store.as_ref() else {
+            return;
+        };
+        let now = Utc::now();
+        let (success, started_at, completed_at) = match status {
+            "running" => (None, Some(now), None),
+            "completed" => (Some(true), None, Some(now)),
+            "failed" | "dead_letter" => (Some(false), None, Some(now)),
+            _ => (None, None, None),
+        };
+        if let Err(e) = store
+            .update_sandbox_job_status(
+                job_id,
+                status,
+                success,
+                failure.as_deref(),
+                started_at,
+                completed_at,
+            )
+            .await
+        {
+            tracing::warn!(job_id = %job_id, error = %e, "Failed to record sandbox job status");
+        }
+        if status == "dead_letter" {
+            notify_dead_letter(store.as_ref(), job_id, failure.as_deref()).await;
+        }
+    }
+
+    /// 1-based position of a queued job, in start order.
+    pub async fn queue_position(&self, job_id: Uuid) -> Option<usize> {
+        let containers = self.containers.read().await;
+        queue_order(&containers)
+            .iter()
+            .position(|h| h.job_id == job_id)
+            .map(|i| i + 1)
+    }
+
+    /// Snapshot of running and waiting jobs with the configured limits.
+    pub async fn queue_snapshot(&self) -> JobQueueSnapshot {
+        let containers = self.containers.read().await;
+        let mut active: Vec<ContainerHandle> = containers
+            .values()
+            .filter(|h| h.is_active())
+            .cloned()
+            .collect();
+        active.sort_by_key(|h| h.started_at);
+        JobQueueSnapshot {
+            max_concurrent_jobs: self.config.max_concurrent_jobs.max(1),
+            max_jobs_per_user: self.config.max_jobs_per_user.max(1),
+            active,
+            queued: queue_order(&containers).into_iter().cloned().collect(),
+        }
     }
 
     /// Inner implementation of container creation (separated for cleanup).
@@ -434,6 +1024,8 @@ impl ContainerJobManager {
                 reason: format!("failed to start container: {}", e),
             })?;
 
+        self.spawn_log_tail(&docker, job_id, &container_id);
+
         // Update handle with container ID
         if let Some(handle) = self.containers.write().await.get_mut(&job_id) {
             handle.container_id = container_id;
@@ -448,16 +1040,26 @@ impl ContainerJobManager {
         Ok(())
     }
 
-    /// Stop a running container job.
+    /// Stop a running container job, or drop it from the queue.
     pub async fn stop_job(&self, job_id: Uuid) -> Result<(), OrchestratorError> {
-        let container_id = {
+        let (container_id, state) = {
             let containers = self.containers.read().await;
             containers
                 .get(&job_id)
-                .map(|h| h.container_id.clone())
+                .map(|h| (h.container_id.clone(), h.state))
                 .ok_or(OrchestratorError::ContainerNotFound { job_id })?
         };
 
+        if state == ContainerState::Queued {
+            if let Some(handle) = self.containers.write().await.get_mut(&job_id) {
+                handle.state = ContainerState::Stopped;
+                handle.queued_at = None;
+            }
+            self.token_store.revoke(job_id).await;
+            tracing::info!(job_id = %job_id, "Removed sandbox job from queue");
+            return Ok(());
+        }
+
         if container_id.is_empty() {
             return Err(OrchestratorError::InvalidContainerState {
                 job_id,
@@ -502,17 +1104,51 @@ impl ContainerJobManager {
 
         tracing::info!(job_id = %job_id, "Stopped worker container");
 
+        self.spawn_dispatch();
         Ok(())
     }
 
     /// Mark a job as complete with a result. The container is stopped but the
     /// handle is kept so `CreateJobTool` can read the completion message.
+    ///
+    /// Failures go through the retry policy first: a retried job is requeued
+    /// instead of stopped, and one that runs out of attempts is recorded as
+    /// `dead_letter`.
     pub async fn complete_job(
         &self,
         job_id: Uuid,
-        result: CompletionResult,
+        mut result: CompletionResult,
     ) -> Result<(), OrchestratorError> {
-        // Store the result before stopping
+        // Stop the container (the handle stays in the map)
+        let (container_id, attempts, project_dir) = {
+            let containers = self.containers.read().await;
+            containers
+                .get(&job_id)
+                .map(|h| (h.container_id.clone(), h.attempt, h.project_dir.clone()))
+                .unwrap_or_default()
+        };
+        if !container_id.is_empty() {
+            self.remove_container(job_id, &container_id, 5).await;
+        }
+
+        let message = result.message.clone().unwrap_or_default();
+        let status = if result.success {
+            "completed"
+        } else {
+            match self.fail_attempt(job_id, classify_failure(&message)).await {
+                FailureOutcome::Retry { .. } => {
+                    self.spawn_dispatch();
+                    return Ok(());
+                }
+                FailureOutcome::Failed => "failed",
+                FailureOutcome::DeadLetter => {
+                    result.message = Some(dead_letter_reason(&message, attempts));
+                    "dead_letter"
+                }
+            }
+        };
+        let failure = (!result.success).then(|| result.message.clone().unwrap_or_default());
+
         {
             let mut containers = self.containers.write().await;
             if let Some(handle) = containers.get_mut(&job_id) {
@@ -520,48 +1156,85 @@ impl ContainerJobManager {
                 handle.state = ContainerState::Stopped;
             }
         }
+        self.token_store.revoke(job_id).await;
+        self.record_status(job_id, status, failure).await;
+        if let Some(dir) = project_dir {
+            self.spawn_artifact_capture(job_id, dir);
+        }
 
-        // Stop container and revoke token (but keep handle in map)
-        let container_id = {
-            let containers = self.containers.read().await;
-            containers.get(&job_id).map(|h| h.container_id.clone())
+        crate::observability::metrics::record_sandbox_job_finished(status);
+        tracing::info!(job_id = %job_id, status, "Completed worker container");
+        self.spawn_dispatch();
+        Ok(())
+    }
+
+    /// Follow a container's stdout/stderr and persist each line as a `log`
+    /// job event, so `/api/jobs/{id}/logs/stream` can tail it. Ends when the
+    /// container goes away.
+    fn spawn_log_tail(&self, docker: &bollard::Docker, job_id: Uuid, container_id: &str) {
+        let Some(store) = self.store.clone() else {
+            return;
         };
-        if let Some(cid) = container_id
-            && !cid.is_empty()
-        {
-            match self.docker().await {
-                Ok(docker) => {
-                    if let Err(e) = docker
-                        .stop_container(
-                            &cid,
-                            Some(bollard::container::StopContainerOptions { t: 5 }),
-                        )
-                        .await
-                    {
-                        tracing::warn!(job_id = %job_id, error = %e, "Failed to stop completed container");
-                    }
-                    if let Err(e) = docker
-                        .remove_container(
-                            &cid,
-                            Some(bollard::container::RemoveContainerOptions {
-                                force: true,
-                                ..Default::default()
-                            }),
-                        )
-                        .await
-                    {
-                        tracing::warn!(job_id = %job_id, error = %e, "Failed to remove completed container");
-                    }
-                }
+        let docker = docker.clone();
+        let container_id = container_id.to_string();
+        tokio::spawn(async move {
+            tail_container_logs(&docker, store.as_ref(), job_id, &container_id).await;
+        });
+    }
+
+    /// Copy and register a finished job's files in the background.
+    fn spawn_artifact_capture(&self, job_id: Uuid, project_dir: PathBuf) {
+        let Some(store) = self.store.clone() else {
+            return;
+        };
+        tokio::spawn(async move {
+            let root = super::artifacts::artifacts_root();
+            match super::artifacts::register_job_artifacts(
+                store.as_ref(),
+                job_id,
+                &project_dir,
+                &root,
+            )
+            .await
+            {
+                Ok(count) => tracing::debug!(job_id = %job_id, count, "Registered job artifacts"),
                 Err(e) => {
-                    tracing::warn!(job_id = %job_id, error = %e, "Failed to connect to Docker for container cleanup");
+                    tracing::warn!(job_id = %job_id, error = %e, "Failed to register job artifacts")
                 }
             }
-        }
-        self.token_store.revoke(job_id).await;
+        });
+    }
 
-        tracing::info!(job_id = %job_id, "Completed worker container");
-        Ok(())
+    /// Best-effort stop and removal of a job's container.
+    async fn remove_container(&self, job_id: Uuid, container_id: &str, grace_secs: i64) {
+        match self.docker().await {
+            Ok(docker) => {
+                if let Err(e) = docker
+                    .stop_container(
+                        container_id,
+                        Some(bollard::container::StopContainerOptions { t: grace_secs }),
+                    )
+                    .await
+                {
+                    tracing::warn!(job_id = %job_id, error = %e, "Failed to stop container");
+                }
+                if let Err(e) = docker
+                    .remove_container(
+                        container_id,
+                        Some(bollard::container::RemoveContainerOptions {
+                            force: true,
+                            ..Default::default()
+                        }),
+                    )
+                    .await
+                {
+                    tracing::warn!(job_id = %job_id, error = %e, "Failed to remove container");
+                }
+            }
+            Err(e) => {
+                tracing::warn!(job_id = %job_id, error = %e, "Failed to connect to Docker for container cleanup");
+            }
+        }
     }
 
     /// Remove a completed job handle from memory (called after result is read).
@@ -592,16 +1265,256 @@ impl ContainerJobManager {
         self.containers.read().await.values().cloned().collect()
     }
 
+    /// Run a short script in an ephemeral container and collect its output.
+    ///
+    /// The container is always removed, including on timeout.
+    pub async fn run_script(
+        &self,
+        run_id: Uuid,
+        run: ScriptRun,
+    ) -> Result<ContainerOutput, OrchestratorError> {
+        let script_dir = validate_bind_mount_path(&run.script_dir, run_id)?;
+        let input_dir = validate_bind_mount_path(&run.input_dir, run_id)?;
+        let output_dir = validate_bind_mount_path(&run.output_dir, run_id)?;
+        let docker = self.docker().await?;
+        let start = std::time::Instant::now();
+
+        use bollard::container::{Config, CreateContainerOptions, RemoveContainerOptions};
+        use bollard::models::HostConfig;
+
+        let memory_mb = run.memory_limit_mb.clamp(64, self.config.memory_limit_mb);
+        let host_config = HostConfig {
+            binds: Some(vec![
+                format!("{}:/workspace/script:ro", script_dir.display()),
+                format!("{}:/workspace/inputs:ro", input_dir.display()),
+                format!("{}:/workspace/outputs:rw", output_dir.display()),
+            ]),
+            memory: Some((memory_mb * 1024 * 1024) as i64),
+            memory_swap: Some((memory_mb * 1024 * 1024) as i64),
+            cpu_shares: Some(self.config.cpu_shares as i64),
+            pids_limit: Some(256),
+            // Scripts work on staged copies only; there is nothing to fetch
+            // and no reason to give privileged discovery data a way out.
+            network_mode: Some("none".to_string()),
+            cap_drop: Some(vec!["ALL".to_string()]),
+            security_opt: Some(vec!["no-new-privileges:true".to_string()]),
+            readonly_rootfs: Some(true),
+            tmpfs: Some(
+                [("/tmp".to_string(), "size=256M".to_string())]
+                    .into_iter()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        let container_config = Config {
+            image: Some(self.config.image.clone()),
+            entrypoint: Some(Vec::new()),
+            cmd: Some(run.command),
+            env: Some(vec![
+                "HOME=/tmp".to_string(),
+                "PYTHONDONTWRITEBYTECODE=1".to_string(),
+            ]),
+            host_config: Some(host_config),
+            user: Some("1000:1000".to_string()),
+            working_dir: Some("/workspace".to_string()),
+            ..Default::default()
+        };
+        let options = CreateContainerOptions {
+            name: format!("clawyer-run-{}", run_id),
+            ..Default::default()
+        };
+        let container_id = docker
+            .create_container(Some(options), container_config)
+            .await
+            .map_err(|e| OrchestratorError::ContainerCreationFailed {
+                job_id: run_id,
+                reason: e.to_string(),
+            })?
+            .id;
+
+        let result = match docker.start_container::<String>(&container_id, None).await {
+            Ok(()) => tokio::time::timeout(
+                run.timeout,
+                wait_and_collect(&docker, &container_id, run.max_output_bytes),
+            )
+            .await
+            .unwrap_or(Err(OrchestratorError::ScriptTimeout {
+                job_id: run_id,
+                timeout: run.timeout,
+            })),
+            Err(e) => Err(OrchestratorError::ContainerCreationFailed {
+                job_id: run_id,
+                reason: format!("failed to start container: {}", e),
+            }),
+        };
+
+        if let Err(e) = docker
+            .remove_container(
+                &container_id,
+                Some(RemoveContainerOptions {
+                    force: true,
+                    ..Default::default()
+                }),
+            )
+            .await
+        {
+            tracing::warn!(run_id = %run_id, error = %e, "Failed to remove script container");
+        }
+
+        result.map(|mut output| {
+            output.duration = start.elapsed();
+            output
+        })
+    }
+
     /// Get a reference to the token store.
     pub fn token_store(&self) -> &TokenStore {
         &self.token_store
     }
 }
 
+/// Split a log chunk into lines, cutting overlong ones.
+fn log_lines(chunk: &str) -> impl Iterator<Item = String> + '_ {
+    chunk
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.chars().take(MAX_LOG_LINE_CHARS).collect())
+}
+
+async fn tail_container_logs(
+    docker: &bollard::Docker,
+    store: &dyn Database,
+    job_id: Uuid,
+    container_id: &str,
+) {
+    use bollard::container::{LogOutput, LogsOptions};
+
+    let mut logs = docker.logs(
+        container_id,
+        Some(LogsOptions::<String> {
+            follow: true,
+            stdout: true,
+            stderr: true,
+            ..Default::default()
+        }),
+    );
+    let mut saved = 0usize;
+    while let Some(chunk) = logs.next().await {
+        let (stream, message) = match chunk {
+            Ok(LogOutput::StdOut { message }) => ("stdout", message),
+            Ok(LogOutput::StdErr { message }) => ("stderr", message),
+            Ok(_) => continue,
+            Err(e) => {
+                tracing::debug!(job_id = %job_id, error = %e, "Container log stream ended");
+                break;
+            }
+        };
+        for line in log_lines(&String::from_utf8_lossy(&message)) {
+            if saved == MAX_LOG_LINES_PER_ATTEMPT {
+                let notice = serde_json::json!({
+                    "stream": "stderr",
+                    "line": format!("[log truncated after {MAX_LOG_LINES_PER_ATTEMPT} lines]"),
+                });
+                let _ = store.save_job_event(job_id, "log", &notice).await;
+                return;
+            }
+            let data = serde_json::json!({ "stream": stream, "line": line });
+            if let Err(e) = store.save_job_event(job_id, "log", &data).await {
+                tracing::warn!(job_id = %job_id, error = %e, "Failed to persist container log line");
+                return;
+            }
+            saved += 1;
+        }
+    }
+}
+
+/// Wait for a script container to exit, then read its logs.
+async fn wait_and_collect(
+    docker: &bollard::Docker,
+    container_id: &str,
+    max_output: usize,
+) -> Result<ContainerOutput, OrchestratorError> {
+    use bollard::container::{LogOutput, LogsOptions, WaitContainerOptions};
+
+    let mut wait = docker.wait_container(
+        container_id,
+        Some(WaitContainerOptions {
+            condition: "not-running",
+        }),
+    );
+    let exit_code = match wait.next().await {
+        Some(Ok(response)) => response.status_code,
+        // bollard reports non-zero exits as an error carrying the code.
+        Some(Err(bollard::errors::Error::DockerContainerWaitError { code, .. })) => code,
+        Some(Err(e)) => {
+            return Err(OrchestratorError::Docker {
+                reason: format!("wait failed: {}", e),
+            });
+        }
+        None => {
+            return Err(OrchestratorError::Docker {
+                reason: "container wait stream ended unexpectedly".to_string(),
+            });
+        }
+    };
+
+    let mut logs = docker.logs(
+        container_id,
+        Some(LogsOptions::<String> {
+            stdout: true,
+            stderr: true,
+            ..Default::default()
+        }),
+    );
+    let half = max_output / 2;
+    let (mut stdout, mut stderr, mut truncated) = (String::new(), String::new(), false);
+    while let Some(chunk) = logs.next().await {
+        let (buf, message) = match chunk {
+            Ok(LogOutput::StdOut { message }) => (&mut stdout, message),
+            Ok(LogOutput::StdErr { message }) => (&mut stderr, message),
+            Ok(_) => continue,
+            Err(e) => {
+                tracing::warn!(error = %e, "Error reading script container logs");
+                break;
+            }
+        };
+        let text = String::from_utf8_lossy(&message);
+        let room = half.saturating_sub(buf.len());
+        if text.len() > room {
+            truncated = true;
+            let mut cut = room;
+            while !text.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            buf.push_str(&text[..cut]);
+        } else {
+            buf.push_str(&text);
+        }
+    }
+
+    Ok(ContainerOutput {
+        exit_code,
+        stdout,
+        stderr,
+        duration: Duration::ZERO,
+        truncated,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn log_lines_skip_blanks_and_cut_long_lines() {
+        let long = "x".repeat(MAX_LOG_LINE_CHARS + 10);
+        let chunk = format!("starting\n\n  \n{long}\n");
+        let lines: Vec<String> = log_lines(&chunk).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "starting");
+        assert_eq!(lines[1].chars().count(), MAX_LOG_LINE_CHARS);
+    }
+
     #[test]
     fn test_container_job_config_default() {
         let config = ContainerJobConfig::default();
@@ -659,6 +1572,279 @@ mod tests {
         );
     }
 
+    fn handle(
+        user_id: &str,
+        priority: JobPriority,
+        state: ContainerState,
+        age_secs: i64,
+    ) -> ContainerHandle {
+        let at = Utc::now() - chrono::Duration::seconds(age_secs);
+        ContainerHandle {
+            job_id: Uuid::new_v4(),
+            container_id: String::new(),
+            state,
+            mode: JobMode::Worker,
+            user_id: user_id.to_string(),
+            priority,
+            created_at: at,
+            started_at: (state == ContainerState::Running).then_some(at),
+            queued_at: (state == ContainerState::Queued).then_some(at),
+            preemptions: 0,
+            attempt: 1,
+            retry_at: None,
+            project_dir: None,
+            task_description: "job".to_string(),
+            last_worker_status: None,
+            worker_iteration: 0,
+            completion_result: None,
+        }
+    }
+
+    fn handles(list: Vec<ContainerHandle>) -> HashMap<Uuid, ContainerHandle> {
+        list.into_iter().map(|h| (h.job_id, h)).collect()
+    }
+
+    fn limits(global: usize, per_user: usize) -> ContainerJobConfig {
+        ContainerJobConfig {
+            max_concurrent_jobs: global,
+            max_jobs_per_user: per_user,
+            ..ContainerJobConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_job_priority_parse_and_order() {
+        assert_eq!(
+            JobPriority::parse(" Interactive"),
+            Some(JobPriority::Interactive)
+        );
+        assert_eq!(JobPriority::parse("urgent"), None);
+        assert!(JobPriority::Interactive > JobPriority::Normal);
+        assert!(JobPriority::Normal > JobPriority::Routine);
+        assert_eq!(JobPriority::default(), JobPriority::Normal);
+    }
+
+    #[test]
+    fn test_plan_admission_enforces_global_and_per_user_limits() {
+        let config = limits(2, 1);
+        let jobs = handles(vec![handle(
+            "alice",
+            JobPriority::Normal,
+            ContainerState::Running,
+            60,
+        )]);
+        assert_eq!(
+            plan_admission(&jobs, &config, "bob", JobPriority::Normal),
+            Admission::Start
+        );
+        assert_eq!(
+            plan_admission(&jobs, &config, "alice", JobPriority::Normal),
+            Admission::Wait
+        );
+
+        let jobs = handles(vec![
+            handle("alice", JobPriority::Normal, ContainerState::Running, 60),
+            handle("bob", JobPriority::Normal, ContainerState::Creating, 5),
+            handle("carol", JobPriority::Normal, ContainerState::Stopped, 5),
+        ]);
+        assert_eq!(
+            plan_admission(&jobs, &config, "carol", JobPriority::Normal),
+            Admission::Wait
+        );
+    }
+
+    #[test]
+    fn test_interactive_job_preempts_newest_routine_job() {
+        let config = limits(2, 2);
+        let old_routine = handle("alice", JobPriority::Routine, ContainerState::Running, 600);
+        let new_routine = handle("bob", JobPriority::Routine, ContainerState::Running, 30);
+        let new_id = new_routine.job_id;
+        let jobs = handles(vec![old_routine, new_routine]);
+
+        assert_eq!(
+            plan_admission(&jobs, &config, "carol", JobPriority::Interactive),
+            Admission::Preempt(new_id)
+        );
+        assert_eq!(
+            plan_admission(&jobs, &config, "carol", JobPriority::Normal),
+            Admission::Wait
+        );
+
+        // A user at their own limit can only displace their own routine job.
+        let config = limits(4, 1);
+        assert_eq!(
+            plan_admission(&jobs, &config, "carol", JobPriority::Interactive),
+            Admission::Start
+        );
+        let normal = handle("carol", JobPriority::Normal, ContainerState::Running, 10);
+        let jobs = handles(vec![normal]);
+        assert_eq!(
+            plan_admission(&jobs, &config, "carol", JobPriority::Interactive),
+            Admission::Wait
+        );
+    }
+
+    #[test]
+    fn test_queue_order_is_priority_then_age() {
+        let routine_old = handle("a", JobPriority::Routine, ContainerState::Queued, 900);
+        let normal_new = handle("a", JobPriority::Normal, ContainerState::Queued, 10);
+        let normal_old = handle("a", JobPriority::Normal, ContainerState::Queued, 100);
+        let interactive = handle("a", JobPriority::Interactive, ContainerState::Queued, 1);
+        let expected = vec![
+            interactive.job_id,
+            normal_old.job_id,
+            normal_new.job_id,
+            routine_old.job_id,
+        ];
+        let jobs = handles(vec![routine_old, normal_new, normal_old, interactive]);
+
+        let order: Vec<Uuid> = queue_order(&jobs).iter().map(|h| h.job_id).collect();
+        assert_eq!(order, expected);
+    }
+
+    #[tokio::test]
+    async fn test_stop_job_removes_queued_job_without_container() {
+        let store = TokenStore::new();
+        let mgr = ContainerJobManager::new(limits(1, 1), store);
+        let running = handle("alice", JobPriority::Normal, ContainerState::Running, 60);
+        let queued = handle("alice", JobPriority::Normal, ContainerState::Queued, 5);
+        let queued_id = queued.job_id;
+        mgr.containers
+            .write()
+            .await
+            .extend(handles(vec![running, queued]));
+        assert_eq!(mgr.queue_position(queued_id).await, Some(1));
+
+        mgr.stop_job(queued_id).await.unwrap();
+
+        let handle = mgr.get_handle(queued_id).await.unwrap();
+        assert_eq!(handle.state, ContainerState::Stopped);
+        assert_eq!(mgr.queue_position(queued_id).await, None);
+        let snapshot = mgr.queue_snapshot().await;
+        assert_eq!(snapshot.active.len(), 1);
+        assert!(snapshot.queued.is_empty());
+    }
+
+    #[test]
+    fn test_settle_failure_requeues_until_attempts_run_out() {
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            initial_backoff: Duration::from_secs(30),
+            max_backoff: Duration::from_secs(60),
+            retry_on: vec![FailureClass::RateLimited],
+        };
+        let now = Utc::now();
+        let mut job = handle("alice", JobPriority::Normal, ContainerState::Running, 60);
+        job.container_id = "abc".to_string();
+
+        assert_eq!(
+            settle_failure(&mut job, &policy, FailureClass::Other, now),
+            FailureOutcome::Failed
+        );
+        assert_eq!(job.state, ContainerState::Running);
+
+        let outcome = settle_failure(&mut job, &policy, FailureClass::RateLimited, now);
+        assert_eq!(
+            outcome,
+            FailureOutcome::Retry {
+                attempt: 2,
+                retry_at: now + chrono::Duration::seconds(30),
+            }
+        );
+        assert_eq!(job.state, ContainerState::Queued);
+        assert!(job.container_id.is_empty());
+
+        assert_eq!(
+            settle_failure(&mut job, &policy, FailureClass::RateLimited, now),
+            FailureOutcome::DeadLetter
+        );
+    }
+
+    #[tokio::test]
+    async fn test_complete_job_retries_then_dead_letters() {
+        let config = ContainerJobConfig {
+            worker_retry: RetryPolicy {
+                max_attempts: 2,
+                initial_backoff: Duration::from_secs(300),
+                ..RetryPolicy::default()
+            },
+            ..ContainerJobConfig::default()
+        };
+        let mgr = ContainerJobManager::new(config, TokenStore::new());
+        let job = handle("alice", JobPriority::Normal, ContainerState::Running, 60);
+        let job_id = job.job_id;
+        mgr.containers.write().await.insert(job_id, job);
+        let failure = || CompletionResult {
+            success: false,
+            message: Some("LLM error: 503 Service Unavailable".to_string()),
+        };
+
+        mgr.complete_job(job_id, failure()).await.unwrap();
+        let retried = mgr.get_handle(job_id).await.unwrap();
+        assert_eq!(retried.state, ContainerState::Queued);
+        assert_eq!(retried.attempt, 2);
+        assert!(retried.retry_at.unwrap() > Utc::now());
+        // Still backing off, so the free slot does not start it.
+        mgr.dispatch_queued().await;
+        assert_eq!(
+            mgr.get_handle(job_id).await.unwrap().state,
+            ContainerState::Queued
+        );
+
+        mgr.containers.write().await.get_mut(&job_id).unwrap().state = ContainerState::Running;
+        mgr.complete_job(job_id, failure()).await.unwrap();
+        let dead = mgr.get_handle(job_id).await.unwrap();
+        assert_eq!(dead.state, ContainerState::Stopped);
+        let result = dead.completion_result.unwrap();
+        assert!(!result.success);
+        assert_eq!(
+            result.message.as_deref(),
+            Some("LLM error: 503 Service Unavailable (gave up after 2 attempts)")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_routine_jobs_wait_out_blackout_dates() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let today = Utc::now().date_naive();
+        db.set_setting(
+            "alice",
+            crate::agent::execution_window::EXECUTION_POLICY_SETTING_KEY,
+            &serde_json::json!({
+                "blackout_dates": [{
+                    "date": today.pred_opt().unwrap(),
+                    "until": today.succ_opt().unwrap(),
+                    "label": "Office closed"
+                }]
+            }),
+        )
+        .await
+        .unwrap();
+        let mgr = ContainerJobManager::new(limits(2, 2), TokenStore::new()).with_store(Some(db));
+        let job_id = Uuid::new_v4();
+
+        let admission = mgr
+            .create_job(
+                job_id,
+                "alice",
+                "reindex matter folders",
+                None,
+                JobMode::Worker,
+                JobPriority::Routine,
+                Vec::new(),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(admission, JobAdmission::Queued { position: 1 }));
+
+        // Free slots do not start it while the blackout lasts.
+        mgr.dispatch_queued().await;
+        let held = mgr.get_handle(job_id).await.unwrap();
+        assert_eq!(held.state, ContainerState::Queued);
+        assert_eq!(held.attempt, 1);
+        assert!(held.retry_at.is_none());
+    }
+
     #[tokio::test]
     async fn test_update_worker_status() {
         let store = TokenStore::new();
@@ -675,7 +1861,14 @@ mod tests {
                     container_id: "test".to_string(),
                     state: ContainerState::Running,
                     mode: JobMode::Worker,
+                    user_id: "default".to_string(),
+                    priority: JobPriority::Normal,
                     created_at: chrono::Utc::now(),
+                    started_at: None,
+                    queued_at: None,
+                    preemptions: 0,
+                    attempt: 1,
+                    retry_at: None,
                     project_dir: None,
                     task_description: "test job".to_string(),
                     last_worker_status: None,
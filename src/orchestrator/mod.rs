@@ -18,7 +18,7 @@
 //! │    POST /worker/{id}/complete                   │
 //! │                                                 │
 //! │  ContainerJobManager                            │
-//! │    create_job() -> container + token             │
+//! │    create_job() -> container or queue slot      │
 //! │    stop_job()                                    │
 //! │    list_jobs()                                   │
 //! │                                                 │
@@ -29,11 +29,15 @@
 //! ```
 
 pub mod api;
+pub mod artifacts;
 pub mod auth;
 pub mod job_manager;
+pub mod retry;
 
 pub use api::OrchestratorApi;
 pub use auth::{CredentialGrant, TokenStore};
 pub use job_manager::{
-    CompletionResult, ContainerHandle, ContainerJobConfig, ContainerJobManager, JobMode,
+    CompletionResult, ContainerHandle, ContainerJobConfig, ContainerJobManager, FailureOutcome,
+    JobAdmission, JobMode, JobPriority, JobQueueSnapshot, ScriptRun,
 };
+pub use retry::{FailureClass, RetryPolicy};
@@ -0,0 +1,207 @@
+//! Retry policies for sandbox jobs.
+//!
+//! A failed attempt is classified from its error message. If the job mode's
+//! policy retries that class and attempts remain, the job goes back to the
+//! queue with an exponential backoff; otherwise it fails outright, or lands
+//! in the dead-letter state once a retryable failure has used up its attempts.
+
+use std::time::Duration;
+
+/// Why a job attempt failed, as far as retrying is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureClass {
+    /// LLM provider returned 429 / rate limit.
+    RateLimited,
+    /// LLM provider returned a 5xx or was unreachable.
+    ProviderError,
+    /// The container could not be created or started.
+    ContainerStart,
+    /// The attempt ran out of time.
+    Timeout,
+    /// Anything else; retrying will most likely fail the same way.
+    Other,
+}
+
+impl FailureClass {
+    pub const ALL: [FailureClass; 5] = [
+        Self::RateLimited,
+        Self::ProviderError,
+        Self::ContainerStart,
+        Self::Timeout,
+        Self::Other,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RateLimited => "rate_limited",
+            Self::ProviderError => "provider_error",
+            Self::ContainerStart => "container_start",
+            Self::Timeout => "timeout",
+            Self::Other => "other",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        Self::ALL
+            .into_iter()
+            .find(|class| class.as_str().eq_ignore_ascii_case(value))
+    }
+}
+
+impl std::fmt::Display for FailureClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Classify a worker-reported failure message.
+pub fn classify_failure(message: &str) -> FailureClass {
+    let lower = message.to_ascii_lowercase();
+    let has_status = |code: &str| {
+        lower
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|word| word == code)
+    };
+    if has_status("429") || lower.contains("rate limit") || lower.contains("too many requests") {
+        FailureClass::RateLimited
+    } else if ["500", "502", "503", "504", "529"]
+        .iter()
+        .any(|code| has_status(code))
+        || lower.contains("bad gateway")
+        || lower.contains("service unavailable")
+        || lower.contains("overloaded")
+        || lower.contains("connection refused")
+        || lower.contains("connection reset")
+    {
+        FailureClass::ProviderError
+    } else if lower.contains("timed out") || lower.contains("timeout") {
+        FailureClass::Timeout
+    } else {
+        FailureClass::Other
+    }
+}
+
+/// How a job mode retries failed attempts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first. `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the second attempt; doubled for each one after.
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay.
+    pub max_backoff: Duration,
+    /// Failure classes worth retrying.
+    pub retry_on: Vec<FailureClass>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_secs(30),
+            max_backoff: Duration::from_secs(600),
+            retry_on: vec![
+                FailureClass::RateLimited,
+                FailureClass::ProviderError,
+                FailureClass::ContainerStart,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Whether a failure of `class` is retried at all.
+    pub fn retries(&self, class: FailureClass) -> bool {
+        self.max_attempts > 1 && self.retry_on.contains(&class)
+    }
+
+    /// Delay before attempt `attempt + 1`, given that `attempt` (1-based) failed.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Parse a comma-separated list of failure classes (`rate_limited,timeout`).
+    pub fn parse_retry_on(value: &str) -> Result<Vec<FailureClass>, String> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(|v| {
+                FailureClass::parse(v).ok_or_else(|| {
+                    let known: Vec<&str> = FailureClass::ALL.iter().map(|c| c.as_str()).collect();
+                    format!(
+                        "unknown failure class '{}' (expected {})",
+                        v,
+                        known.join(", ")
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_provider_and_container_failures() {
+        assert_eq!(
+            classify_failure("LLM error: HTTP 429 Too Many Requests"),
+            FailureClass::RateLimited
+        );
+        assert_eq!(
+            classify_failure("proxy returned 502 Bad Gateway"),
+            FailureClass::ProviderError
+        );
+        assert_eq!(
+            classify_failure("provider overloaded, try again"),
+            FailureClass::ProviderError
+        );
+        assert_eq!(
+            classify_failure("Timed out (10 minutes)"),
+            FailureClass::Timeout
+        );
+        // Numbers that merely contain a status code do not count.
+        assert_eq!(
+            classify_failure("parsed 15003 rows then hit a syntax error"),
+            FailureClass::Other
+        );
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicy {
+            max_attempts: 6,
+            initial_backoff: Duration::from_secs(10),
+            max_backoff: Duration::from_secs(60),
+            retry_on: vec![FailureClass::Timeout],
+        };
+        assert_eq!(policy.backoff(1), Duration::from_secs(10));
+        assert_eq!(policy.backoff(2), Duration::from_secs(20));
+        assert_eq!(policy.backoff(3), Duration::from_secs(40));
+        assert_eq!(policy.backoff(4), Duration::from_secs(60));
+        assert!(policy.retries(FailureClass::Timeout));
+        assert!(!policy.retries(FailureClass::RateLimited));
+
+        let disabled = RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        };
+        assert!(!disabled.retries(FailureClass::RateLimited));
+    }
+
+    #[test]
+    fn parses_retry_on_lists() {
+        assert_eq!(
+            RetryPolicy::parse_retry_on("rate_limited, Timeout,").unwrap(),
+            vec![FailureClass::RateLimited, FailureClass::Timeout]
+        );
+        let err = RetryPolicy::parse_retry_on("rate_limited,flaky").unwrap_err();
+        assert!(err.contains("flaky"), "{err}");
+    }
+}
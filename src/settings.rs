@@ -123,13 +123,17 @@ pub struct EmbeddingsSettings {
     #[serde(default)]
     pub enabled: bool,
 
-    /// Provider to use: "openai" or "nearai".
+    /// Provider to use: "openai", "nearai", "ollama", or "local".
     #[serde(default = "default_embeddings_provider")]
     pub provider: String,
 
     /// Model to use for embeddings.
     #[serde(default = "default_embeddings_model")]
     pub model: String,
+
+    /// Directory holding `model.onnx` and `tokenizer.json` for the local provider.
+    #[serde(default)]
+    pub local_model_dir: Option<String>,
 }
 
 fn default_embeddings_provider() -> String {
@@ -146,6 +150,7 @@ impl Default for EmbeddingsSettings {
             enabled: false,
             provider: default_embeddings_provider(),
             model: default_embeddings_model(),
+            local_model_dir: None,
         }
     }
 }
@@ -256,6 +261,22 @@ pub struct ChannelSettings {
     #[serde(default)]
     pub telegram_owner_id: Option<i64>,
 
+    /// Gateway chat rate limit per user, as `<requests>/<window_secs>` or `off`.
+    #[serde(default)]
+    pub gateway_rate_limit_chat: Option<String>,
+
+    /// Gateway upload rate limit per user, as `<requests>/<window_secs>` or `off`.
+    #[serde(default)]
+    pub gateway_rate_limit_uploads: Option<String>,
+
+    /// Gateway rate limit per user for all other API routes.
+    #[serde(default)]
+    pub gateway_rate_limit_api: Option<String>,
+
+    /// Rate limit per client address for public share-link downloads.
+    #[serde(default)]
+    pub gateway_rate_limit_shares: Option<String>,
+
     /// Enabled WASM channels by name.
     /// Channels not in this list but present in the channels directory will still load.
     /// This is primarily used by the setup wizard to track which channels were configured.
@@ -649,6 +670,10 @@ pub struct LegalSettings {
     #[serde(default = "default_true")]
     pub conflict_reindex_on_startup: bool,
 
+    /// Hold every outbound message to an external channel for human review.
+    #[serde(default)]
+    pub supervised_mode: bool,
+
     /// Network controls for legal mode.
     #[serde(default)]
     pub network: LegalNetworkSettings,
@@ -664,6 +689,14 @@ pub struct LegalSettings {
     /// Matter workspace encryption controls.
     #[serde(default)]
     pub encryption: LegalEncryptionSettings,
+
+    /// Per-matter storage quota controls.
+    #[serde(default)]
+    pub storage: LegalStorageSettings,
+
+    /// Per-task / per-confidentiality LLM routing.
+    #[serde(default)]
+    pub llm_routing: LegalLlmRoutingSettings,
 }
 
 fn default_legal_jurisdiction() -> String {
@@ -696,10 +729,13 @@ impl Default for LegalSettings {
             conflict_check_enabled: true,
             conflict_file_fallback_enabled: true,
             conflict_reindex_on_startup: true,
+            supervised_mode: false,
             network: LegalNetworkSettings::default(),
             audit: LegalAuditSettings::default(),
             redaction: LegalRedactionSettings::default(),
             encryption: LegalEncryptionSettings::default(),
+            storage: LegalStorageSettings::default(),
+            llm_routing: LegalLlmRoutingSettings::default(),
         }
     }
 }
@@ -807,6 +843,51 @@ impl Default for LegalEncryptionSettings {
     }
 }
 
+/// Legal per-matter storage quota settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegalStorageSettings {
+    /// Soft quota per matter in megabytes (unset = unlimited).
+    #[serde(default)]
+    pub soft_quota_mb: Option<u64>,
+    /// Warn once a matter reaches this percentage of its soft quota.
+    #[serde(default = "default_legal_quota_warn_percent")]
+    pub warn_at_percent: u8,
+}
+
+/// Legal LLM routing controls.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LegalLlmRoutingSettings {
+    /// Routing rules, checked in order.
+    #[serde(default)]
+    pub routes: Vec<LegalLlmRouteSettings>,
+}
+
+/// One LLM routing rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegalLlmRouteSettings {
+    /// "drafting", "summarization", or "extraction"; unset matches any task.
+    #[serde(default)]
+    pub task: Option<String>,
+    /// Matter confidentiality level from `matter.yaml`; unset matches any.
+    #[serde(default)]
+    pub confidentiality: Option<String>,
+    /// `backend:model` targets in failover order.
+    pub models: Vec<String>,
+}
+
+fn default_legal_quota_warn_percent() -> u8 {
+    80
+}
+
+impl Default for LegalStorageSettings {
+    fn default() -> Self {
+        Self {
+            soft_quota_mb: None,
+            warn_at_percent: default_legal_quota_warn_percent(),
+        }
+    }
+}
+
 impl Settings {
     /// Reconstruct Settings from a flat key-value map (as stored in the DB).
     ///
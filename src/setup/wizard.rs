@@ -1526,6 +1526,7 @@ impl SetupWizard {
                 circuit_breaker_threshold: None,
                 circuit_breaker_recovery_secs: 30,
                 response_cache_enabled: false,
+                response_cache_persistent: true,
                 response_cache_ttl_secs: 3600,
                 response_cache_max_entries: 1000,
                 failover_cooldown_secs: 300,
@@ -1537,6 +1538,7 @@ impl SetupWizard {
             ollama: None,
             openai_compatible: None,
             tinfoil: None,
+            air_gapped: false,
         };
 
         match create_llm_provider(&config, session) {
@@ -297,6 +297,7 @@ impl TestHarnessBuilder {
                 .expect("default legal config should resolve"),
             hooks,
             cost_guard,
+            routine_engine_slot: None,
         };
 
         TestHarness {
@@ -0,0 +1,301 @@
+//! Fact extraction tool feeding the matter chronology.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::context::JobContext;
+use crate::db::{CreateMatterFactParams, Database, MatterFactQuery};
+use crate::legal::chronology::{
+    chronology_path, extract_facts, key_facts_path, render_chronology, render_key_facts,
+};
+use crate::llm::LlmProvider;
+use crate::tools::tool::{Tool, ToolError, ToolOutput, ToolRateLimitConfig, db_err, require_str};
+use crate::workspace::Workspace;
+
+/// Extracts dated facts from a matter document into the fact store and
+/// regenerates the matter's key facts log and chronology.
+pub struct ExtractMatterFactsTool {
+    workspace: Arc<Workspace>,
+    store: Arc<dyn Database>,
+    llm: Arc<dyn LlmProvider>,
+    legal: Option<crate::config::LegalConfig>,
+}
+
+impl ExtractMatterFactsTool {
+    pub fn new(
+        workspace: Arc<Workspace>,
+        store: Arc<dyn Database>,
+        llm: Arc<dyn LlmProvider>,
+    ) -> Self {
+        Self {
+            workspace,
+            store,
+            llm,
+            legal: None,
+        }
+    }
+
+    pub fn with_legal_policy(mut self, legal: crate::config::LegalConfig) -> Self {
+        self.legal = Some(legal);
+        self
+    }
+
+    /// Rewrite the key facts log and chronology from every stored fact.
+    async fn write_fact_tables(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<(usize, String, String), ToolError> {
+        let facts = self
+            .store
+            .list_matter_facts(user_id, matter_id, &MatterFactQuery::default())
+            .await
+            .map_err(db_err)?;
+        let matter_root = crate::legal::policy::matter_root(self.legal.as_ref());
+        let key_facts = key_facts_path(matter_root, matter_id);
+        let chronology = chronology_path(matter_root, matter_id);
+        for (path, content) in [
+            (&key_facts, render_key_facts(&facts)),
+            (&chronology, render_chronology(&facts)),
+        ] {
+            self.workspace
+                .write(path, &content)
+                .await
+                .map_err(|e| ToolError::ExecutionFailed(format!("Write failed: {e}")))?;
+        }
+        Ok((facts.len(), key_facts, chronology))
+    }
+}
+
+#[async_trait]
+impl Tool for ExtractMatterFactsTool {
+    fn name(&self) -> &str {
+        "extract_matter_facts"
+    }
+
+    fn description(&self) -> &str {
+        "Extract dated facts (agreements, notices, payments, filings, hearings) from a matter \
+         document, store them with source citations, and regenerate the matter's \
+         facts/key_facts.md and templates/chronology.md tables. Re-running on the same \
+         document replaces its earlier facts."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Workspace path of the document inside a matter folder, e.g. 'matters/acme-v-foo/correspondence/demand.md'"
+                },
+                "use_llm": {
+                    "type": "boolean",
+                    "description": "Also ask the LLM for facts (default true); false uses only the date scan",
+                    "default": true
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: &JobContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let start = Instant::now();
+        let source_path = require_str(&params, "path")?.trim().trim_matches('/');
+        let use_llm = params
+            .get("use_llm")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        if source_path.split('/').any(|part| part == "..") {
+            return Err(ToolError::InvalidParameters(
+                "path must not contain '..' segments".to_string(),
+            ));
+        }
+        let matter_root = crate::legal::policy::matter_root(self.legal.as_ref());
+        let matter_id =
+            crate::legal::workspace_crypto::matter_id_for_path(source_path, matter_root)
+                .ok_or_else(|| {
+                    ToolError::InvalidParameters(format!(
+                        "path must be inside a matter folder under '{}/'",
+                        matter_root
+                    ))
+                })?;
+        if let Some(legal) = self.legal.as_ref().filter(|l| l.enabled)
+            && let Some(active) = super::memory::active_matter_for_ctx(legal, ctx)
+            && active != matter_id
+        {
+            return Err(ToolError::NotAuthorized(format!(
+                "document belongs to matter '{matter_id}' but the active matter is '{active}'"
+            )));
+        }
+        // The generated tables would otherwise feed their own rows back in.
+        if source_path == key_facts_path(matter_root, &matter_id)
+            || source_path == chronology_path(matter_root, &matter_id)
+        {
+            return Err(ToolError::InvalidParameters(
+                "path is a generated fact table; extract from the underlying documents".to_string(),
+            ));
+        }
+
+        let source = self
+            .workspace
+            .read(source_path)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Read failed: {e}")))?;
+        let llm = use_llm.then(|| self.llm.as_ref());
+        let facts = extract_facts(llm, source_path, &source.content).await;
+
+        let replaced = self
+            .store
+            .delete_matter_facts_for_source(&ctx.user_id, &matter_id, source_path)
+            .await
+            .map_err(db_err)?;
+        let mut stored = Vec::with_capacity(facts.len());
+        for fact in &facts {
+            let record = self
+                .store
+                .create_matter_fact(
+                    &ctx.user_id,
+                    &matter_id,
+                    &CreateMatterFactParams {
+                        fact_date: fact.date,
+                        description: fact.description.clone(),
+                        source_path: source_path.to_string(),
+                        source_locator: fact.locator.clone(),
+                        source_excerpt: fact.excerpt.clone(),
+                        confidence: f64::from(fact.confidence),
+                        extractor: fact.extractor.to_string(),
+                        created_by: ctx.user_id.clone(),
+                    },
+                )
+                .await
+                .map_err(db_err)?;
+            stored.push(serde_json::json!({
+                "id": record.id.to_string(),
+                "date": record.fact_date.format("%Y-%m-%d").to_string(),
+                "description": record.description,
+                "source_locator": record.source_locator,
+                "confidence": record.confidence,
+                "extractor": record.extractor,
+            }));
+        }
+
+        let (total, key_facts, chronology) =
+            self.write_fact_tables(&ctx.user_id, &matter_id).await?;
+
+        Ok(ToolOutput::success(
+            serde_json::json!({
+                "matter_id": matter_id,
+                "source_path": source_path,
+                "facts": stored,
+                "replaced": replaced,
+                "matter_fact_count": total,
+                "key_facts_path": key_facts,
+                "chronology_path": chronology,
+            }),
+            start.elapsed(),
+        ))
+    }
+
+    fn execution_timeout(&self) -> Duration {
+        Duration::from_secs(120)
+    }
+
+    fn requires_sanitization(&self) -> bool {
+        false
+    }
+
+    fn rate_limit_config(&self) -> Option<ToolRateLimitConfig> {
+        Some(ToolRateLimitConfig::new(20, 200))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::StubLlm;
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn extracts_facts_and_regenerates_tables() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", Arc::clone(&db)));
+        let source = "matters/acme/correspondence/demand.md";
+        workspace
+            .write(
+                source,
+                "On March 3, 2023, the parties signed the supply agreement.\n\
+                 Acme sent a notice of breach on October 2, 2023.\n",
+            )
+            .await
+            .expect("write source");
+        let tool = ExtractMatterFactsTool::new(
+            Arc::clone(&workspace),
+            Arc::clone(&db),
+            Arc::new(StubLlm::default()),
+        );
+        let params = serde_json::json!({"path": source, "use_llm": false});
+
+        let output = tool
+            .execute(params.clone(), &JobContext::default())
+            .await
+            .expect("extract");
+        assert_eq!(output.result["matter_id"], "acme");
+        assert_eq!(output.result["facts"].as_array().map(Vec::len), Some(2));
+        assert_eq!(output.result["replaced"], 0);
+
+        let chronology = workspace
+            .read("matters/acme/templates/chronology.md")
+            .await
+            .expect("read chronology");
+        assert!(chronology.content.contains("| Date | Event | Source |"));
+        assert!(chronology.content.contains(
+            "| 2023-03-03 | On March 3, 2023, the parties signed the supply agreement. | matters/acme/correspondence/demand.md (line 1) |"
+        ));
+        let key_facts = workspace
+            .read("matters/acme/facts/key_facts.md")
+            .await
+            .expect("read key facts");
+        assert!(
+            key_facts
+                .content
+                .contains("| 2023-10-02: Acme sent a notice")
+        );
+
+        // Re-extraction replaces rather than duplicates.
+        let output = tool
+            .execute(params, &JobContext::default())
+            .await
+            .expect("re-extract");
+        assert_eq!(output.result["replaced"], 2);
+        assert_eq!(output.result["matter_fact_count"], 2);
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn rejects_paths_outside_matters_and_generated_tables() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", Arc::clone(&db)));
+        let tool = ExtractMatterFactsTool::new(workspace, db, Arc::new(StubLlm::default()));
+        for path in [
+            "notes/demand.md",
+            "matters/acme/../other/secret.md",
+            "matters/acme/templates/chronology.md",
+        ] {
+            let err = tool
+                .execute(serde_json::json!({"path": path}), &JobContext::default())
+                .await
+                .expect_err(path);
+            assert!(
+                matches!(err, ToolError::InvalidParameters(_)),
+                "{path}: {err}"
+            );
+        }
+    }
+}
@@ -0,0 +1,313 @@
+//! Document comparison tool: last draft vs. executed copy.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::context::JobContext;
+use crate::db::Database;
+use crate::legal::compare::{
+    ChangeMemo, compare_documents, default_memo_title, render_change_memo,
+};
+use crate::legal::memo::{file_memo_document, memo_path};
+use crate::tools::tool::{Tool, ToolError, ToolOutput, require_str};
+use crate::workspace::Workspace;
+
+/// Most changes returned in one call; the counts still cover every change.
+const MAX_CHANGES: usize = 50;
+
+/// Compares two matter documents paragraph by paragraph, separating
+/// substantive changes from formatting noise, and optionally files a change
+/// memo in the matter.
+pub struct CompareDocumentsTool {
+    workspace: Arc<Workspace>,
+    store: Option<Arc<dyn Database>>,
+    legal: Option<crate::config::LegalConfig>,
+}
+
+impl CompareDocumentsTool {
+    pub fn new(workspace: Arc<Workspace>, store: Option<Arc<dyn Database>>) -> Self {
+        Self {
+            workspace,
+            store,
+            legal: None,
+        }
+    }
+
+    pub fn with_legal_policy(mut self, legal: crate::config::LegalConfig) -> Self {
+        self.legal = Some(legal);
+        self
+    }
+
+    /// Resolve the matter a document belongs to, refusing paths outside the
+    /// matter root and matters other than the active one.
+    fn document_matter(
+        &self,
+        field: &str,
+        path: &str,
+        ctx: &JobContext,
+    ) -> Result<String, ToolError> {
+        if path.split('/').any(|part| part == "..") {
+            return Err(ToolError::InvalidParameters(format!(
+                "{field} must not contain '..' segments"
+            )));
+        }
+        let matter_root = crate::legal::policy::matter_root(self.legal.as_ref());
+        let Some(matter_id) = crate::legal::workspace_crypto::matter_id_for_path(path, matter_root)
+        else {
+            return Err(ToolError::InvalidParameters(format!(
+                "{field} must be a document under '{}/<matter>/'",
+                matter_root
+            )));
+        };
+        if let Some(legal) = self.legal.as_ref().filter(|l| l.enabled)
+            && let Some(active) = super::memory::active_matter_for_ctx(legal, ctx)
+            && active != matter_id
+        {
+            return Err(ToolError::NotAuthorized(format!(
+                "document belongs to matter '{matter_id}' but the active matter is '{active}'"
+            )));
+        }
+        Ok(matter_id)
+    }
+
+    async fn read(&self, path: &str) -> Result<String, ToolError> {
+        self.workspace
+            .read(path)
+            .await
+            .map(|doc| doc.content)
+            .map_err(|e| ToolError::ExecutionFailed(format!("Read of '{path}' failed: {e}")))
+    }
+}
+
+#[async_trait]
+impl Tool for CompareDocumentsTool {
+    fn name(&self) -> &str {
+        "compare_documents"
+    }
+
+    fn description(&self) -> &str {
+        "Compare two documents in the same matter, typically the last draft against the \
+         executed contract (upload the signed PDF's extracted text to the matter first). \
+         Page headers and footers, line wrapping, hyphenation, quote styles, capitalization, \
+         and punctuation are reported as formatting; wording, figure, and obligation changes, \
+         added or deleted paragraphs are reported as substantive with a word-level redline. \
+         With 'write_memo', files a change memo under the matter's memos/ folder for the \
+         client file."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "base_path": {
+                    "type": "string",
+                    "description": "Workspace path of the earlier document, e.g. 'matters/acme-v-foo/drafts/spa-v7.md'"
+                },
+                "compared_path": {
+                    "type": "string",
+                    "description": "Workspace path of the later document, e.g. 'matters/acme-v-foo/executed/spa.md'"
+                },
+                "write_memo": {
+                    "type": "boolean",
+                    "description": "File a change memo in the matter's memos/ folder (default false)"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Memo title (default: 'Changes from <base> to <compared>')"
+                }
+            },
+            "required": ["base_path", "compared_path"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: &JobContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let start = Instant::now();
+        let base_path = require_str(&params, "base_path")?
+            .trim()
+            .trim_matches('/')
+            .to_string();
+        let compared_path = require_str(&params, "compared_path")?
+            .trim()
+            .trim_matches('/')
+            .to_string();
+        let write_memo = params
+            .get("write_memo")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let title = params
+            .get("title")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| default_memo_title(&base_path, &compared_path));
+
+        let matter_id = self.document_matter("base_path", &base_path, ctx)?;
+        if self.document_matter("compared_path", &compared_path, ctx)? != matter_id {
+            return Err(ToolError::InvalidParameters(
+                "base_path and compared_path must belong to the same matter".to_string(),
+            ));
+        }
+        if base_path == compared_path {
+            return Err(ToolError::InvalidParameters(
+                "base_path and compared_path must be different documents".to_string(),
+            ));
+        }
+
+        let base = self.read(&base_path).await?;
+        let compared = self.read(&compared_path).await?;
+        let comparison = compare_documents(&base, &compared);
+
+        let memo = if write_memo {
+            let prepared_at = Utc::now();
+            let path = memo_path(
+                crate::legal::policy::matter_root(self.legal.as_ref()),
+                &matter_id,
+                prepared_at,
+                &title,
+            );
+            let content = render_change_memo(
+                &ChangeMemo {
+                    matter_id: &matter_id,
+                    title: &title,
+                    base_path: &base_path,
+                    compared_path: &compared_path,
+                    prepared_by: &ctx.user_id,
+                    prepared_at,
+                },
+                &comparison,
+            );
+            // Register the memo as a matter document when the matter has a
+            // database record; otherwise it is only written to the workspace.
+            let store = match self.store.as_ref() {
+                Some(store) => store
+                    .get_matter_db(&ctx.user_id, &matter_id)
+                    .await
+                    .map_err(|e| ToolError::ExecutionFailed(format!("Matter lookup failed: {e}")))?
+                    .map(|_| store),
+                None => None,
+            };
+            match store {
+                Some(store) => {
+                    file_memo_document(
+                        store.as_ref(),
+                        self.workspace.as_ref(),
+                        &ctx.user_id,
+                        &matter_id,
+                        &path,
+                        &title,
+                        &content,
+                    )
+                    .await
+                    .map_err(|e| ToolError::ExecutionFailed(format!("Filing memo failed: {e}")))?;
+                }
+                None => {
+                    self.workspace.write(&path, &content).await.map_err(|e| {
+                        ToolError::ExecutionFailed(format!("Writing memo failed: {e}"))
+                    })?;
+                }
+            }
+            Some((path, store.is_some()))
+        } else {
+            None
+        };
+
+        let output = serde_json::json!({
+            "matter_id": matter_id,
+            "base_path": base_path,
+            "compared_path": compared_path,
+            "identical": comparison.identical(),
+            "base_paragraphs": comparison.base_paragraphs,
+            "compared_paragraphs": comparison.compared_paragraphs,
+            "unchanged": comparison.unchanged,
+            "substantive": comparison.substantive,
+            "formatting": comparison.formatting,
+            "changes": comparison.changes.iter().take(MAX_CHANGES).collect::<Vec<_>>(),
+            "changes_truncated": comparison.changes.len() > MAX_CHANGES,
+            "memo_path": memo.as_ref().map(|(path, _)| path),
+            "memo_registered": memo.as_ref().is_some_and(|(_, registered)| *registered),
+        });
+        Ok(ToolOutput::success(output, start.elapsed()))
+    }
+
+    fn requires_sanitization(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn compares_draft_with_executed_copy_and_files_memo() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", Arc::clone(&db)));
+        workspace
+            .write(
+                "matters/acme/drafts/lease.md",
+                "1. Rent. The tenant shall pay $2,000 per month.\n\n2. Repairs. The tenant shall repair the roof.\n",
+            )
+            .await
+            .expect("write draft");
+        workspace
+            .write(
+                "matters/acme/executed/lease.md",
+                "1. Rent. The tenant shall pay $2,000 per\nmonth.\n\nPage 1 of 1\n\n2. Repairs. The tenant may repair the roof.\n",
+            )
+            .await
+            .expect("write executed text");
+        let tool = CompareDocumentsTool::new(Arc::clone(&workspace), Some(Arc::clone(&db)));
+        let ctx = JobContext::default();
+
+        let output = tool
+            .execute(
+                serde_json::json!({
+                    "base_path": "matters/acme/drafts/lease.md",
+                    "compared_path": "matters/acme/executed/lease.md",
+                    "write_memo": true,
+                }),
+                &ctx,
+            )
+            .await
+            .expect("compare");
+        let result = &output.result;
+        assert_eq!(result["matter_id"], "acme");
+        assert_eq!(result["unchanged"], 1, "{result}");
+        assert_eq!(result["substantive"], 1, "{result}");
+        assert_eq!(result["formatting"], 0, "{result}");
+        assert_eq!(
+            result["changes"][0]["notes"],
+            serde_json::json!(["Obligation wording changed: shall → may"])
+        );
+        let memo_path = result["memo_path"].as_str().expect("memo path");
+        assert!(memo_path.starts_with("matters/acme/memos/"), "{memo_path}");
+        assert_eq!(result["memo_registered"], false);
+        let memo = workspace.read(memo_path).await.expect("read memo");
+        assert!(
+            memo.content.contains("[-shall-] {+may+}"),
+            "{}",
+            memo.content
+        );
+
+        let err = tool
+            .execute(
+                serde_json::json!({
+                    "base_path": "matters/acme/drafts/lease.md",
+                    "compared_path": "matters/other/executed/lease.md",
+                }),
+                &ctx,
+            )
+            .await
+            .expect_err("cross-matter comparison");
+        assert!(matches!(err, ToolError::InvalidParameters(_)), "{err}");
+    }
+}
@@ -0,0 +1,565 @@
+//! Matter correspondence drafting tool.
+//!
+//! `draft` writes an email into the matter's `communications/outbox/`
+//! without sending anything. `send` always requires explicit approval
+//! through the tool approval prompt (session auto-approve does not apply),
+//! then hands the current outbox file to the email tool and records the
+//! outcome in the matter's contact log.
+
+use std::sync::{Arc, Weak};
+use std::time::Instant;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::context::JobContext;
+use crate::legal::correspondence::{
+    CONTACT_LOG_TEMPLATE, EmailDraft, EmailStatus, contact_log_path, contact_log_row,
+    format_timestamp, is_plausible_address, outbox_path, parse_addresses, parse_email_draft,
+    render_email_draft, send_params, sent_path,
+};
+use crate::legal::status_report::EMAIL_TOOL_NAME;
+use crate::tools::registry::ToolRegistry;
+use crate::tools::tool::{
+    ApprovalRequirement, Tool, ToolError, ToolOutput, ToolRateLimitConfig, require_str,
+};
+use crate::workspace::Workspace;
+
+const MAX_RECIPIENTS: usize = 20;
+const MAX_SUBJECT_CHARS: usize = 200;
+
+/// Drafts matter email into the outbox and sends approved drafts.
+pub struct ComposeEmailTool {
+    workspace: Arc<Workspace>,
+    /// Used to look up the installed email tool at send time. Weak because
+    /// this tool is itself held by the registry.
+    registry: Weak<ToolRegistry>,
+    legal: Option<crate::config::LegalConfig>,
+}
+
+impl ComposeEmailTool {
+    pub fn new(workspace: Arc<Workspace>, registry: Weak<ToolRegistry>) -> Self {
+        Self {
+            workspace,
+            registry,
+            legal: None,
+        }
+    }
+
+    pub fn with_legal_policy(mut self, legal: crate::config::LegalConfig) -> Self {
+        self.legal = Some(legal);
+        self
+    }
+
+    fn check_active_matter(&self, ctx: &JobContext, matter_id: &str) -> Result<(), ToolError> {
+        if let Some(legal) = self.legal.as_ref().filter(|l| l.enabled)
+            && let Some(active) = super::memory::active_matter_for_ctx(legal, ctx)
+            && active != matter_id
+        {
+            return Err(ToolError::NotAuthorized(format!(
+                "email belongs to matter '{matter_id}' but the active matter is '{active}'"
+            )));
+        }
+        Ok(())
+    }
+
+    fn resolve_matter(
+        &self,
+        params: &serde_json::Value,
+        ctx: &JobContext,
+    ) -> Result<String, ToolError> {
+        let explicit = params
+            .get("matter_id")
+            .and_then(|v| v.as_str())
+            .and_then(crate::legal::policy::sanitize_optional_matter_id);
+        let active = self
+            .legal
+            .as_ref()
+            .filter(|l| l.enabled)
+            .and_then(|legal| super::memory::active_matter_for_ctx(legal, ctx));
+        let matter_id = explicit.or(active).ok_or_else(|| {
+            ToolError::InvalidParameters(
+                "matter_id is required when no matter is active".to_string(),
+            )
+        })?;
+        self.check_active_matter(ctx, &matter_id)?;
+        Ok(matter_id)
+    }
+
+    fn recipients(params: &serde_json::Value, name: &str) -> Result<Vec<String>, ToolError> {
+        let list = match params.get(name) {
+            None | Some(serde_json::Value::Null) => Vec::new(),
+            Some(serde_json::Value::String(raw)) => parse_addresses(raw),
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .filter_map(|v| v.as_str())
+                .flat_map(parse_addresses)
+                .collect(),
+            Some(_) => {
+                return Err(ToolError::InvalidParameters(format!(
+                    "{name} must be an address or a list of addresses"
+                )));
+            }
+        };
+        if let Some(bad) = list.iter().find(|addr| !is_plausible_address(addr)) {
+            return Err(ToolError::InvalidParameters(format!(
+                "'{bad}' is not a valid email address"
+            )));
+        }
+        Ok(list)
+    }
+
+    async fn draft(
+        &self,
+        params: &serde_json::Value,
+        ctx: &JobContext,
+    ) -> Result<serde_json::Value, ToolError> {
+        let matter_id = self.resolve_matter(params, ctx)?;
+        let metadata_path = format!(
+            "{}/{matter_id}/matter.yaml",
+            crate::legal::policy::matter_root(self.legal.as_ref())
+        );
+        if !self
+            .workspace
+            .exists(&metadata_path)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?
+        {
+            return Err(ToolError::InvalidParameters(format!(
+                "matter '{matter_id}' not found"
+            )));
+        }
+        let to = Self::recipients(params, "to")?;
+        if to.is_empty() {
+            return Err(ToolError::InvalidParameters(
+                "at least one 'to' recipient is required".to_string(),
+            ));
+        }
+        let cc = Self::recipients(params, "cc")?;
+        if to.len() + cc.len() > MAX_RECIPIENTS {
+            return Err(ToolError::InvalidParameters(format!(
+                "at most {MAX_RECIPIENTS} recipients are allowed"
+            )));
+        }
+        let subject = require_str(params, "subject")?.trim();
+        if subject.is_empty() || subject.chars().count() > MAX_SUBJECT_CHARS {
+            return Err(ToolError::InvalidParameters(format!(
+                "subject must be 1-{MAX_SUBJECT_CHARS} characters"
+            )));
+        }
+        let body = require_str(params, "body")?;
+        if body.trim().is_empty() {
+            return Err(ToolError::InvalidParameters(
+                "body cannot be empty".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        let draft = EmailDraft {
+            matter_id: matter_id.clone(),
+            to,
+            cc,
+            subject: subject.to_string(),
+            body: body.to_string(),
+            status: EmailStatus::Draft,
+            drafted_by: ctx.user_id.clone(),
+            drafted_at: format_timestamp(now),
+            sent_at: None,
+            approved_by: None,
+            delivery_error: None,
+        };
+        let path = outbox_path(
+            crate::legal::policy::matter_root(self.legal.as_ref()),
+            &matter_id,
+            now,
+            subject,
+        );
+        self.workspace
+            .write(&path, &render_email_draft(&draft))
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Write failed: {e}")))?;
+        Ok(serde_json::json!({
+            "matter_id": matter_id,
+            "path": path,
+            "status": draft.status.as_str(),
+            "to": draft.to,
+            "cc": draft.cc,
+            "subject": draft.subject,
+            "next_step": "Review or edit the draft, then call compose_email with action 'send' and this path. Sending requires explicit approval.",
+        }))
+    }
+
+    async fn append_contact_log(
+        &self,
+        draft: &EmailDraft,
+        document_path: &str,
+    ) -> Result<(), ToolError> {
+        let path = contact_log_path(
+            crate::legal::policy::matter_root(self.legal.as_ref()),
+            &draft.matter_id,
+        );
+        let existing = match self.workspace.read(&path).await {
+            Ok(doc) if !doc.content.trim().is_empty() => doc.content,
+            _ => CONTACT_LOG_TEMPLATE.to_string(),
+        };
+        let updated = format!(
+            "{}\n{}",
+            existing.trim_end(),
+            contact_log_row(draft, Utc::now(), document_path)
+        );
+        self.workspace
+            .write(&path, &updated)
+            .await
+            .map(|_| ())
+            .map_err(|e| ToolError::ExecutionFailed(format!("Contact log write failed: {e}")))
+    }
+
+    async fn send(
+        &self,
+        params: &serde_json::Value,
+        ctx: &JobContext,
+    ) -> Result<serde_json::Value, ToolError> {
+        let path = require_str(params, "path")?.trim().trim_matches('/');
+        if path.split('/').any(|part| part == "..") {
+            return Err(ToolError::InvalidParameters(
+                "path must not contain '..' segments".to_string(),
+            ));
+        }
+        let sent_copy = sent_path(path).ok_or_else(|| {
+            ToolError::InvalidParameters(
+                "path must be a draft in a matter's communications/outbox/ folder".to_string(),
+            )
+        })?;
+        let matter_root = crate::legal::policy::matter_root(self.legal.as_ref());
+        let path_matter = crate::legal::workspace_crypto::matter_id_for_path(path, matter_root)
+            .ok_or_else(|| {
+                ToolError::InvalidParameters(format!(
+                    "path must be inside a matter folder under '{}/'",
+                    matter_root
+                ))
+            })?;
+        self.check_active_matter(ctx, &path_matter)?;
+
+        let doc = self
+            .workspace
+            .read(path)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Read failed: {e}")))?;
+        let mut draft = parse_email_draft(&doc.content).ok_or_else(|| {
+            ToolError::ExecutionFailed(format!(
+                "'{path}' is not a compose_email draft (header block missing or edited)"
+            ))
+        })?;
+        if draft.matter_id != path_matter {
+            return Err(ToolError::ExecutionFailed(format!(
+                "draft header names matter '{}' but the file is in matter '{path_matter}'",
+                draft.matter_id
+            )));
+        }
+        if draft.status == EmailStatus::Sent {
+            return Err(ToolError::InvalidParameters(format!(
+                "'{path}' has already been sent"
+            )));
+        }
+        if let Some(bad) = draft
+            .to
+            .iter()
+            .chain(draft.cc.iter())
+            .find(|addr| !is_plausible_address(addr))
+        {
+            return Err(ToolError::ExecutionFailed(format!(
+                "draft recipient '{bad}' is not a valid email address"
+            )));
+        }
+        if draft.to.is_empty() || draft.body.trim().is_empty() {
+            return Err(ToolError::ExecutionFailed(
+                "draft has no recipient or no body".to_string(),
+            ));
+        }
+
+        let email_tool = match self.registry.upgrade() {
+            Some(registry) => registry.get(EMAIL_TOOL_NAME).await,
+            None => None,
+        }
+        .ok_or_else(|| {
+            ToolError::ExecutionFailed(format!(
+                "email tool '{EMAIL_TOOL_NAME}' is not installed; the draft was not sent"
+            ))
+        })?;
+        let mut send_ctx = ctx.clone();
+        send_ctx.metadata = serde_json::json!({ "matter_id": draft.matter_id });
+        let result = email_tool.execute(send_params(&draft), &send_ctx).await;
+
+        draft.approved_by = Some(ctx.user_id.clone());
+        match result {
+            Ok(_) => {
+                draft.status = EmailStatus::Sent;
+                draft.sent_at = Some(format_timestamp(Utc::now()));
+                draft.delivery_error = None;
+                self.workspace
+                    .write(&sent_copy, &render_email_draft(&draft))
+                    .await
+                    .map_err(|e| ToolError::ExecutionFailed(format!("Write failed: {e}")))?;
+                if let Err(e) = self.workspace.delete(path).await {
+                    tracing::warn!(path = %path, error = %e, "Failed to remove sent draft from outbox");
+                }
+                self.append_contact_log(&draft, &sent_copy).await?;
+                Ok(serde_json::json!({
+                    "matter_id": draft.matter_id,
+                    "status": draft.status.as_str(),
+                    "sent_path": sent_copy,
+                    "to": draft.to,
+                    "cc": draft.cc,
+                    "subject": draft.subject,
+                    "email_tool": EMAIL_TOOL_NAME,
+                }))
+            }
+            Err(err) => {
+                let error = err.to_string();
+                draft.status = EmailStatus::DeliveryFailed;
+                draft.delivery_error = Some(error.clone());
+                self.workspace
+                    .write(path, &render_email_draft(&draft))
+                    .await
+                    .map_err(|e| ToolError::ExecutionFailed(format!("Write failed: {e}")))?;
+                self.append_contact_log(&draft, path).await?;
+                Err(ToolError::ExecutionFailed(format!(
+                    "Email delivery failed; the draft stays in the outbox: {error}"
+                )))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ComposeEmailTool {
+    fn name(&self) -> &str {
+        "compose_email"
+    }
+
+    fn description(&self) -> &str {
+        "Draft matter correspondence and send it after attorney approval. Action 'draft' writes \
+         the email into the matter's communications/outbox/ folder and sends nothing. Action \
+         'send' delivers an outbox draft through the email tool; it always asks for explicit \
+         approval, then files the sent copy under communications/sent/ and logs it in the \
+         matter's contact log."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["draft", "send"],
+                    "description": "'draft' to write a new outbox draft, 'send' to deliver an existing one"
+                },
+                "matter_id": {
+                    "type": "string",
+                    "description": "Matter the email belongs to (draft only; defaults to the active matter)"
+                },
+                "to": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Recipient addresses (draft only)"
+                },
+                "cc": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Cc addresses (draft only)"
+                },
+                "subject": {
+                    "type": "string",
+                    "description": "Subject line (draft only)"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Plain-text message body (draft only)"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Outbox draft to send, e.g. 'matters/acme-v-foo/communications/outbox/2026-10-14-153000-settlement-offer.md' (send only)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: &JobContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let start = Instant::now();
+        let result = match require_str(&params, "action")? {
+            "draft" => self.draft(&params, ctx).await?,
+            "send" => self.send(&params, ctx).await?,
+            other => {
+                return Err(ToolError::InvalidParameters(format!(
+                    "unknown action '{other}'; use 'draft' or 'send'"
+                )));
+            }
+        };
+        Ok(ToolOutput::success(result, start.elapsed()))
+    }
+
+    fn requires_approval(&self, params: &serde_json::Value) -> ApprovalRequirement {
+        match params.get("action").and_then(|v| v.as_str()) {
+            Some("draft") => ApprovalRequirement::Never,
+            _ => ApprovalRequirement::Always,
+        }
+    }
+
+    fn requires_sanitization(&self) -> bool {
+        false
+    }
+
+    fn rate_limit_config(&self) -> Option<ToolRateLimitConfig> {
+        Some(ToolRateLimitConfig::new(20, 200))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::web::test_support::RecordingEmailTool;
+
+    async fn setup(
+        fail: bool,
+    ) -> (
+        Arc<ToolRegistry>,
+        Arc<RecordingEmailTool>,
+        Arc<Workspace>,
+        tempfile::TempDir,
+    ) {
+        let (db, dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", db));
+        workspace
+            .write("matters/acme/matter.yaml", "matter_id: acme\n")
+            .await
+            .unwrap();
+        let registry = Arc::new(ToolRegistry::new());
+        let email = Arc::new(if fail {
+            RecordingEmailTool::failing("quota exceeded")
+        } else {
+            RecordingEmailTool::new(Arc::default())
+        });
+        registry.register_sync(email.clone());
+        registry.register_sync(Arc::new(ComposeEmailTool::new(
+            Arc::clone(&workspace),
+            Arc::downgrade(&registry),
+        )));
+        (registry, email, workspace, dir)
+    }
+
+    async fn draft(registry: &ToolRegistry) -> String {
+        let tool = registry.get("compose_email").await.unwrap();
+        let out = tool
+            .execute(
+                serde_json::json!({
+                    "action": "draft",
+                    "matter_id": "acme",
+                    "to": ["client@example.com"],
+                    "subject": "Settlement offer",
+                    "body": "The other side has offered $50,000."
+                }),
+                &JobContext::default(),
+            )
+            .await
+            .expect("draft");
+        out.result["path"].as_str().unwrap().to_string()
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn only_send_requires_approval() {
+        let (registry, _email, _workspace, _dir) = setup(false).await;
+        let tool = registry.get("compose_email").await.unwrap();
+        assert_eq!(
+            tool.requires_approval(&serde_json::json!({"action": "draft"})),
+            ApprovalRequirement::Never
+        );
+        for params in [serde_json::json!({"action": "send"}), serde_json::json!({})] {
+            assert_eq!(tool.requires_approval(&params), ApprovalRequirement::Always);
+        }
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn send_files_sent_copy_and_logs_contact() {
+        let (registry, email, workspace, _dir) = setup(false).await;
+        let path = draft(&registry).await;
+        assert!(path.starts_with("matters/acme/communications/outbox/"));
+        assert!(email.sent.lock().unwrap().is_empty());
+
+        // Attorney edits the body before approving.
+        let edited = workspace
+            .read(&path)
+            .await
+            .unwrap()
+            .content
+            .replace("$50,000", "$55,000");
+        workspace.write(&path, &edited).await.unwrap();
+
+        let tool = registry.get("compose_email").await.unwrap();
+        let out = tool
+            .execute(
+                serde_json::json!({"action": "send", "path": path}),
+                &JobContext::default(),
+            )
+            .await
+            .expect("send");
+        let sent_path = out.result["sent_path"].as_str().unwrap();
+        assert!(sent_path.starts_with("matters/acme/communications/sent/"));
+
+        let sent = email.sent.lock().unwrap().clone();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0]["to"], "client@example.com");
+        assert!(sent[0]["body"].as_str().unwrap().contains("$55,000"));
+
+        assert!(!workspace.exists(&path).await.unwrap());
+        let copy = workspace.read(sent_path).await.unwrap().content;
+        assert!(copy.contains("- **Status:** sent"));
+        let log = workspace
+            .read("matters/acme/communications/contact_log.md")
+            .await
+            .unwrap()
+            .content;
+        assert!(log.starts_with("# Communications Log"));
+        assert!(log.contains("| client@example.com | Email | Sent \"Settlement offer\""));
+
+        let err = tool
+            .execute(
+                serde_json::json!({"action": "send", "path": path}),
+                &JobContext::default(),
+            )
+            .await
+            .expect_err("draft is gone after sending");
+        assert!(matches!(err, ToolError::ExecutionFailed(_)), "{err}");
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn failed_delivery_keeps_draft_in_outbox() {
+        let (registry, _email, workspace, _dir) = setup(true).await;
+        let path = draft(&registry).await;
+        let tool = registry.get("compose_email").await.unwrap();
+        let err = tool
+            .execute(
+                serde_json::json!({"action": "send", "path": path}),
+                &JobContext::default(),
+            )
+            .await
+            .expect_err("delivery fails");
+        assert!(err.to_string().contains("quota exceeded"));
+
+        let draft = workspace.read(&path).await.unwrap().content;
+        assert!(draft.contains("- **Status:** delivery_failed"));
+        let log = workspace
+            .read("matters/acme/communications/contact_log.md")
+            .await
+            .unwrap()
+            .content;
+        assert!(log.contains("Delivery failed"));
+    }
+}
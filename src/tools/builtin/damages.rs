@@ -0,0 +1,440 @@
+//! Damages calculator tool.
+//!
+//! Keeps a per-matter damages model in the workspace and regenerates the
+//! matter's damages summary table after every change.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+
+use crate::context::JobContext;
+use crate::legal::damages::{
+    DamagesItem, DamagesKind, DamagesModel, calculate_damages, damages_model_path,
+    damages_summary_path, rate_profile_for_jurisdiction, render_damages_summary, validate_rate,
+};
+use crate::tools::tool::{Tool, ToolError, ToolOutput, require_str};
+use crate::workspace::Workspace;
+
+/// Model fields `update_model` accepts; `null` clears one.
+const DATE_FIELDS: [&str; 3] = ["accrual_date", "judgment_date", "valuation_date"];
+const RATE_FIELDS: [&str; 4] = [
+    "prejudgment_rate",
+    "general_prejudgment_rate",
+    "postjudgment_rate",
+    "discount_rate",
+];
+
+/// Builds damages models and their summary tables.
+pub struct DamagesCalculatorTool {
+    workspace: Arc<Workspace>,
+    legal: Option<crate::config::LegalConfig>,
+}
+
+impl DamagesCalculatorTool {
+    pub fn new(workspace: Arc<Workspace>) -> Self {
+        Self {
+            workspace,
+            legal: None,
+        }
+    }
+
+    pub fn with_legal_policy(mut self, legal: crate::config::LegalConfig) -> Self {
+        self.legal = Some(legal);
+        self
+    }
+
+    /// Stored model, or a new one seeded with the matter's jurisdiction.
+    async fn load_model(
+        &self,
+        matter_id: &str,
+        model_path: &str,
+    ) -> Result<DamagesModel, ToolError> {
+        let metadata = crate::legal::matter::read_matter_metadata_for_root(
+            &self.workspace,
+            crate::legal::policy::matter_root(self.legal.as_ref()),
+            matter_id,
+        )
+        .await
+        .map_err(|e| ToolError::InvalidParameters(format!("matter '{matter_id}': {e}")))?;
+        match self.workspace.read(model_path).await {
+            Ok(doc) if !doc.content.trim().is_empty() => serde_json::from_str(&doc.content)
+                .map_err(|e| {
+                    ToolError::ExecutionFailed(format!(
+                        "'{model_path}' is not a damages model: {e}"
+                    ))
+                }),
+            _ => Ok(DamagesModel {
+                jurisdiction: metadata.jurisdiction,
+                ..DamagesModel::default()
+            }),
+        }
+    }
+}
+
+fn decimal_value(name: &str, value: &serde_json::Value) -> Result<Decimal, ToolError> {
+    let raw = match value {
+        serde_json::Value::Number(n) => n.to_string(),
+        serde_json::Value::String(s) => s.trim().replace([',', '$'], ""),
+        _ => String::new(),
+    };
+    raw.parse::<Decimal>()
+        .map_err(|_| ToolError::InvalidParameters(format!("{name} must be a number, got {value}")))
+}
+
+fn optional_decimal(params: &serde_json::Value, name: &str) -> Result<Option<Decimal>, ToolError> {
+    match params.get(name) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(value) => decimal_value(name, value).map(Some),
+    }
+}
+
+fn optional_date(params: &serde_json::Value, name: &str) -> Result<Option<NaiveDate>, ToolError> {
+    match params.get(name) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(value) => value
+            .as_str()
+            .and_then(|raw| NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d").ok())
+            .map(Some)
+            .ok_or_else(|| {
+                ToolError::InvalidParameters(format!("{name} must be a YYYY-MM-DD date"))
+            }),
+    }
+}
+
+fn optional_text(params: &serde_json::Value, name: &str) -> Option<String> {
+    params
+        .get(name)
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+fn item_from_params(params: &serde_json::Value) -> Result<DamagesItem, ToolError> {
+    let kind = DamagesKind::parse(require_str(params, "kind")?).ok_or_else(|| {
+        ToolError::InvalidParameters("kind must be 'special' or 'general'".to_string())
+    })?;
+    let amount = params
+        .get("amount")
+        .ok_or_else(|| ToolError::InvalidParameters("missing 'amount' parameter".to_string()))
+        .and_then(|value| decimal_value("amount", value))?;
+    let annual_years = match params.get("annual_years") {
+        None | Some(serde_json::Value::Null) => None,
+        Some(value) => Some(
+            value
+                .as_u64()
+                .and_then(|years| u32::try_from(years).ok())
+                .ok_or_else(|| {
+                    ToolError::InvalidParameters("annual_years must be a whole number".to_string())
+                })?,
+        ),
+    };
+    Ok(DamagesItem {
+        id: String::new(),
+        kind,
+        description: require_str(params, "description")?.trim().to_string(),
+        amount,
+        date: optional_date(params, "date")?,
+        years_until_due: optional_decimal(params, "years_until_due")?,
+        annual_years,
+        source: optional_text(params, "source"),
+    })
+}
+
+/// Apply `update_model` fields present in `params`.
+fn update_model(model: &mut DamagesModel, params: &serde_json::Value) -> Result<(), ToolError> {
+    if let Some(value) = params.get("jurisdiction") {
+        model.jurisdiction = value
+            .as_str()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string);
+    }
+    for name in DATE_FIELDS {
+        if params.get(name).is_none() {
+            continue;
+        }
+        let date = optional_date(params, name)?;
+        match name {
+            "accrual_date" => model.accrual_date = date,
+            "judgment_date" => model.judgment_date = date,
+            _ => model.valuation_date = date,
+        }
+    }
+    for name in RATE_FIELDS {
+        if params.get(name).is_none() {
+            continue;
+        }
+        let rate = optional_decimal(params, name)?;
+        if let Some(rate) = rate {
+            validate_rate(name, rate).map_err(ToolError::InvalidParameters)?;
+        }
+        match name {
+            "prejudgment_rate" => model.prejudgment_rate = rate,
+            "general_prejudgment_rate" => model.general_prejudgment_rate = rate,
+            "postjudgment_rate" => model.postjudgment_rate = rate,
+            _ => model.discount_rate = rate,
+        }
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl Tool for DamagesCalculatorTool {
+    fn name(&self) -> &str {
+        "damages"
+    }
+
+    fn description(&self) -> &str {
+        "Build and value a matter's damages model. Actions: 'add_item' (special or general \
+         damages line item; future losses via years_until_due or annual_years), 'remove_item', \
+         'update_model' (jurisdiction, accrual/judgment/valuation dates, interest and discount \
+         rates as annual percentages), and 'calculate'. Pre- and post-judgment interest use the \
+         jurisdiction's statutory rates unless rates are set on the model; future losses are \
+         discounted to present value. Every action rewrites damages/damages_summary.md."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "matter_id": {
+                    "type": "string",
+                    "description": "Matter to model"
+                },
+                "action": {
+                    "type": "string",
+                    "enum": ["add_item", "remove_item", "update_model", "calculate"],
+                    "description": "What to do (default 'calculate')"
+                },
+                "kind": {
+                    "type": "string",
+                    "enum": ["special", "general"],
+                    "description": "add_item: special (pecuniary) or general (non-pecuniary) damages"
+                },
+                "description": {
+                    "type": "string",
+                    "description": "add_item: line item description, e.g. 'MRI, St. Mary's'"
+                },
+                "amount": {
+                    "type": "number",
+                    "description": "add_item: amount, or the yearly amount with annual_years"
+                },
+                "date": {
+                    "type": "string",
+                    "description": "add_item: YYYY-MM-DD the loss was incurred (prejudgment interest start)"
+                },
+                "years_until_due": {
+                    "type": "number",
+                    "description": "add_item: future loss payable this many years from the valuation date"
+                },
+                "annual_years": {
+                    "type": "integer",
+                    "description": "add_item: future loss recurring yearly for this many years"
+                },
+                "source": {
+                    "type": "string",
+                    "description": "add_item: receipt, invoice, or report supporting the amount"
+                },
+                "item_id": {
+                    "type": "string",
+                    "description": "remove_item: line item id, e.g. 'S2'"
+                },
+                "jurisdiction": {"type": "string", "description": "update_model: jurisdiction code, e.g. 'NY' or 'ON'"},
+                "accrual_date": {"type": "string", "description": "update_model: cause-of-action date (YYYY-MM-DD)"},
+                "judgment_date": {"type": "string", "description": "update_model: judgment date (YYYY-MM-DD)"},
+                "valuation_date": {"type": "string", "description": "update_model: date to value the model at (default today)"},
+                "prejudgment_rate": {"type": "number", "description": "update_model: prejudgment interest %/year"},
+                "general_prejudgment_rate": {"type": "number", "description": "update_model: prejudgment interest %/year on general damages"},
+                "postjudgment_rate": {"type": "number", "description": "update_model: post-judgment interest %/year"},
+                "discount_rate": {"type": "number", "description": "update_model: discount rate %/year for future losses"}
+            },
+            "required": ["matter_id"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: &JobContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let start = Instant::now();
+        let matter_id =
+            crate::legal::policy::sanitize_optional_matter_id(require_str(&params, "matter_id")?)
+                .ok_or_else(|| ToolError::InvalidParameters("matter_id is empty".to_string()))?;
+        if let Some(scoped) = crate::legal::policy::matter_id_from_metadata(&ctx.metadata)
+            && scoped != matter_id
+        {
+            return Err(ToolError::NotAuthorized(format!(
+                "this job is scoped to matter '{scoped}'"
+            )));
+        }
+        let action = params
+            .get("action")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .unwrap_or("calculate");
+
+        let matter_prefix = format!(
+            "{}/{matter_id}",
+            crate::legal::policy::matter_root(self.legal.as_ref())
+        );
+        let model_path = damages_model_path(&matter_prefix);
+        let mut model = self.load_model(&matter_id, &model_path).await?;
+        let mut changed = serde_json::Value::Null;
+        match action {
+            "add_item" => {
+                let item = model
+                    .add_item(item_from_params(&params)?)
+                    .map_err(ToolError::InvalidParameters)?;
+                changed = serde_json::json!(item);
+            }
+            "remove_item" => {
+                let item_id = require_str(&params, "item_id")?;
+                let removed = model.remove_item(item_id).ok_or_else(|| {
+                    ToolError::InvalidParameters(format!("no damages item '{item_id}'"))
+                })?;
+                changed = serde_json::json!(removed);
+            }
+            "update_model" => update_model(&mut model, &params)?,
+            "calculate" => {}
+            other => {
+                return Err(ToolError::InvalidParameters(format!(
+                    "unknown action '{other}' (expected add_item, remove_item, update_model, or calculate)"
+                )));
+            }
+        }
+
+        let profile = rate_profile_for_jurisdiction(model.jurisdiction.as_deref())
+            .map_err(ToolError::ExecutionFailed)?;
+        let now = Utc::now();
+        let calculation = calculate_damages(&model, profile, now.date_naive())
+            .map_err(ToolError::InvalidParameters)?;
+        let model_json = serde_json::to_string_pretty(&model)
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+        self.workspace
+            .write(&model_path, &model_json)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Write failed: {e}")))?;
+        let summary_path = damages_summary_path(&matter_prefix);
+        self.workspace
+            .write(
+                &summary_path,
+                &render_damages_summary(&matter_id, &model, &calculation, now),
+            )
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Write failed: {e}")))?;
+
+        let mut output = serde_json::json!({
+            "matter_id": matter_id,
+            "action": action,
+            "model_path": model_path,
+            "summary_path": summary_path,
+            "jurisdiction": model.jurisdiction,
+            "calculation": calculation,
+        });
+        if !changed.is_null() {
+            output["item"] = changed;
+        }
+        Ok(ToolOutput::success(output, start.elapsed()))
+    }
+
+    fn requires_sanitization(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn builds_model_and_rewrites_summary() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", Arc::clone(&db)));
+        workspace
+            .write(
+                "matters/demo/matter.yaml",
+                "matter_id: demo\nclient: Demo Client\nteam:\n  - Lead Counsel\n\
+                 confidentiality: attorney-client-privileged\nadversaries:\n  - Example Co\n\
+                 retention: follow-firm-policy\njurisdiction: NY\n",
+            )
+            .await
+            .expect("seed matter");
+        let tool = DamagesCalculatorTool::new(Arc::clone(&workspace));
+        let ctx = JobContext::default();
+
+        let output = tool
+            .execute(
+                serde_json::json!({
+                    "matter_id": "demo",
+                    "action": "add_item",
+                    "kind": "special",
+                    "description": "Hospital bills",
+                    "amount": "10,000",
+                    "date": "2024-07-01"
+                }),
+                &ctx,
+            )
+            .await
+            .expect("add item");
+        assert_eq!(output.result["item"]["id"], "S1");
+        assert_eq!(output.result["jurisdiction"], "NY");
+
+        let output = tool
+            .execute(
+                serde_json::json!({
+                    "matter_id": "demo",
+                    "action": "update_model",
+                    "judgment_date": "2025-01-01",
+                    "valuation_date": "2025-01-01"
+                }),
+                &ctx,
+            )
+            .await
+            .expect("update model");
+        let calculation = &output.result["calculation"];
+        let amount =
+            |value: &serde_json::Value| value.as_str().unwrap().parse::<Decimal>().unwrap();
+        assert_eq!(
+            amount(&calculation["prejudgment_interest"]),
+            Decimal::new(45370, 2)
+        );
+        assert_eq!(
+            amount(&calculation["rates"]["prejudgment"]),
+            Decimal::from(9)
+        );
+
+        let summary = workspace
+            .read("matters/demo/damages/damages_summary.md")
+            .await
+            .expect("summary");
+        assert!(
+            summary
+                .content
+                .contains("| Prejudgment interest | $453.70 |")
+        );
+        let model = workspace
+            .read("matters/demo/damages/damages_model.json")
+            .await
+            .expect("model");
+        assert!(model.content.contains("\"judgment_date\": \"2025-01-01\""));
+
+        for params in [
+            serde_json::json!({"matter_id": "demo", "action": "remove_item", "item_id": "S9"}),
+            serde_json::json!({"matter_id": "demo", "action": "update_model", "discount_rate": 250}),
+            serde_json::json!({"matter_id": "missing"}),
+        ] {
+            let err = tool.execute(params.clone(), &ctx).await.expect_err("error");
+            assert!(
+                matches!(err, ToolError::InvalidParameters(_)),
+                "{params}: {err}"
+            );
+        }
+    }
+}
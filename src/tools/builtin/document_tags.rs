@@ -0,0 +1,257 @@
+//! Document tagging tool for review workflows.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::context::JobContext;
+use crate::db::{Database, SetDocumentTagParams};
+use crate::tools::tool::{Tool, ToolError, ToolOutput, ToolRateLimitConfig, db_err, require_str};
+use crate::workspace::Workspace;
+use crate::workspace::tags::{normalize_tag_key, normalize_tag_value};
+
+/// Sets and removes key/value tags on workspace documents.
+pub struct TagDocumentTool {
+    workspace: Arc<Workspace>,
+    store: Arc<dyn Database>,
+    legal: Option<crate::config::LegalConfig>,
+}
+
+impl TagDocumentTool {
+    pub fn new(workspace: Arc<Workspace>, store: Arc<dyn Database>) -> Self {
+        Self {
+            workspace,
+            store,
+            legal: None,
+        }
+    }
+
+    pub fn with_legal_policy(mut self, legal: crate::config::LegalConfig) -> Self {
+        self.legal = Some(legal);
+        self
+    }
+}
+
+/// Tags to set from the `tags` object. `true` and `null` set a flag tag.
+fn parse_tag_params(params: &serde_json::Value) -> Result<Vec<SetDocumentTagParams>, ToolError> {
+    let Some(tags) = params.get("tags") else {
+        return Ok(Vec::new());
+    };
+    let tags = tags.as_object().ok_or_else(|| {
+        ToolError::InvalidParameters("'tags' must be an object of key: value".to_string())
+    })?;
+    tags.iter()
+        .map(|(key, value)| {
+            let value = match value {
+                serde_json::Value::Null | serde_json::Value::Bool(true) => None,
+                serde_json::Value::String(s) => Some(s.as_str()),
+                _ => {
+                    return Err(ToolError::InvalidParameters(format!(
+                        "tag '{key}' must have a string value, true, or null"
+                    )));
+                }
+            };
+            Ok(SetDocumentTagParams {
+                key: normalize_tag_key(key).map_err(ToolError::InvalidParameters)?,
+                value: normalize_tag_value(value).map_err(ToolError::InvalidParameters)?,
+            })
+        })
+        .collect()
+}
+
+#[async_trait]
+impl Tool for TagDocumentTool {
+    fn name(&self) -> &str {
+        "tag_document"
+    }
+
+    fn description(&self) -> &str {
+        "Tag a workspace document during review with key/value labels such as \
+         hot-doc, responsive, or privilege=attorney-client, or remove tags. Tags \
+         can be used to filter the document list and search. Returns the \
+         document's tags after the change."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Workspace path of the document, e.g. 'matters/acme-v-foo/discovery/email-0412.md'"
+                },
+                "tags": {
+                    "type": "object",
+                    "description": "Tags to set, e.g. {\"responsive\": true, \"privilege\": \"attorney-client\"}. Use true or null for a tag without a value; setting an existing key replaces its value.",
+                    "additionalProperties": {"type": ["string", "boolean", "null"]}
+                },
+                "remove": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Tag keys to remove"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: &JobContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let start = Instant::now();
+        let path = require_str(&params, "path")?.trim().trim_matches('/');
+        if path.is_empty() || path.split('/').any(|part| part == "..") {
+            return Err(ToolError::InvalidParameters(
+                "path must be a workspace document path without '..' segments".to_string(),
+            ));
+        }
+        let tags = parse_tag_params(&params)?;
+        let remove = match params.get("remove") {
+            None => Vec::new(),
+            Some(serde_json::Value::Array(keys)) => keys
+                .iter()
+                .map(|key| {
+                    let key = key.as_str().ok_or_else(|| {
+                        ToolError::InvalidParameters("'remove' must list tag keys".to_string())
+                    })?;
+                    normalize_tag_key(key).map_err(ToolError::InvalidParameters)
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            Some(_) => {
+                return Err(ToolError::InvalidParameters(
+                    "'remove' must list tag keys".to_string(),
+                ));
+            }
+        };
+        if tags.is_empty() && remove.is_empty() {
+            return Err(ToolError::InvalidParameters(
+                "provide 'tags' to set or 'remove' keys".to_string(),
+            ));
+        }
+
+        if let Some(legal) = self.legal.as_ref().filter(|l| l.enabled)
+            && let Some(active) = super::memory::active_matter_for_ctx(legal, ctx)
+            && let Some(matter_id) = crate::legal::workspace_crypto::matter_id_for_path(
+                path,
+                crate::legal::policy::matter_root(self.legal.as_ref()),
+            )
+            && active != matter_id
+        {
+            return Err(ToolError::NotAuthorized(format!(
+                "document belongs to matter '{matter_id}' but the active matter is '{active}'"
+            )));
+        }
+        if !self
+            .workspace
+            .exists(path)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Lookup failed: {e}")))?
+        {
+            return Err(ToolError::InvalidParameters(format!(
+                "document '{path}' does not exist"
+            )));
+        }
+
+        let mut removed = Vec::new();
+        for key in &remove {
+            if self
+                .store
+                .remove_document_tag(&ctx.user_id, path, key)
+                .await
+                .map_err(db_err)?
+            {
+                removed.push(key.clone());
+            }
+        }
+        let records = self
+            .store
+            .set_document_tags(&ctx.user_id, path, &tags, &ctx.user_id)
+            .await
+            .map_err(db_err)?;
+        let tags: serde_json::Map<String, serde_json::Value> = records
+            .into_iter()
+            .map(|tag| {
+                (
+                    tag.key,
+                    tag.value
+                        .map(serde_json::Value::String)
+                        .unwrap_or(serde_json::Value::Bool(true)),
+                )
+            })
+            .collect();
+
+        Ok(ToolOutput::success(
+            serde_json::json!({
+                "path": path,
+                "tags": tags,
+                "removed": removed,
+            }),
+            start.elapsed(),
+        ))
+    }
+
+    fn execution_timeout(&self) -> Duration {
+        Duration::from_secs(30)
+    }
+
+    fn requires_sanitization(&self) -> bool {
+        false
+    }
+
+    fn rate_limit_config(&self) -> Option<ToolRateLimitConfig> {
+        Some(ToolRateLimitConfig::new(120, 2000))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn tags_and_untags_existing_documents() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", Arc::clone(&db)));
+        let path = "matters/acme/discovery/email-0412.md";
+        workspace
+            .write(path, "Re: supply schedule")
+            .await
+            .expect("write doc");
+        let tool = TagDocumentTool::new(Arc::clone(&workspace), Arc::clone(&db));
+
+        let output = tool
+            .execute(
+                serde_json::json!({
+                    "path": path,
+                    "tags": {"Hot Doc": true, "privilege": "attorney-client"},
+                }),
+                &JobContext::default(),
+            )
+            .await
+            .expect("tag");
+        assert_eq!(output.result["tags"]["hot-doc"], true);
+        assert_eq!(output.result["tags"]["privilege"], "attorney-client");
+
+        let output = tool
+            .execute(
+                serde_json::json!({"path": path, "remove": ["privilege"]}),
+                &JobContext::default(),
+            )
+            .await
+            .expect("untag");
+        assert_eq!(output.result["removed"], serde_json::json!(["privilege"]));
+        assert!(output.result["tags"].get("privilege").is_none());
+
+        let err = tool
+            .execute(
+                serde_json::json!({"path": "matters/acme/missing.md", "tags": {"hot-doc": true}}),
+                &JobContext::default(),
+            )
+            .await
+            .expect_err("missing document");
+        assert!(matches!(err, ToolError::InvalidParameters(_)), "{err}");
+    }
+}
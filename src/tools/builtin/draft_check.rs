@@ -0,0 +1,239 @@
+//! Deterministic draft checking tool.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+
+use crate::context::JobContext;
+use crate::legal::draft_check::{CHECKS, check_draft};
+use crate::legal::filing_validation::IssueSeverity;
+use crate::tools::tool::{Tool, ToolError, ToolOutput};
+use crate::workspace::Workspace;
+
+/// Most issues returned in one call; the counts still cover every issue.
+const MAX_ISSUES: usize = 100;
+
+/// Runs rule-based consistency checks (defined terms, party names,
+/// cross-references, numbering, typing slips) over a draft.
+pub struct DraftCheckTool {
+    workspace: Arc<Workspace>,
+    legal: Option<crate::config::LegalConfig>,
+}
+
+impl DraftCheckTool {
+    pub fn new(workspace: Arc<Workspace>) -> Self {
+        Self {
+            workspace,
+            legal: None,
+        }
+    }
+
+    pub fn with_legal_policy(mut self, legal: crate::config::LegalConfig) -> Self {
+        self.legal = Some(legal);
+        self
+    }
+
+    /// Read a workspace draft, refusing files from a matter other than the
+    /// active one.
+    async fn read_draft(
+        &self,
+        path: &str,
+        ctx: &JobContext,
+    ) -> Result<(String, Option<String>), ToolError> {
+        if path.is_empty() {
+            return Err(ToolError::InvalidParameters(
+                "path must not be empty".to_string(),
+            ));
+        }
+        if path.split('/').any(|part| part == "..") {
+            return Err(ToolError::InvalidParameters(
+                "path must not contain '..' segments".to_string(),
+            ));
+        }
+        let matter_id = crate::legal::workspace_crypto::matter_id_for_path(
+            path,
+            crate::legal::policy::matter_root(self.legal.as_ref()),
+        );
+        if let Some(matter_id) = matter_id.as_deref()
+            && let Some(legal) = self.legal.as_ref().filter(|l| l.enabled)
+            && let Some(active) = super::memory::active_matter_for_ctx(legal, ctx)
+            && active != matter_id
+        {
+            return Err(ToolError::NotAuthorized(format!(
+                "draft belongs to matter '{matter_id}' but the active matter is '{active}'"
+            )));
+        }
+        let doc = self
+            .workspace
+            .read(path)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Read failed: {e}")))?;
+        Ok((doc.content, matter_id))
+    }
+}
+
+fn requested_checks(params: &serde_json::Value) -> Result<Vec<&str>, ToolError> {
+    let Some(raw) = params.get("checks").filter(|v| !v.is_null()) else {
+        return Ok(Vec::new());
+    };
+    raw.as_array()
+        .ok_or_else(|| ToolError::InvalidParameters("checks must be an array".to_string()))?
+        .iter()
+        .map(|value| {
+            value.as_str().map(str::trim).ok_or_else(|| {
+                ToolError::InvalidParameters("checks must contain only strings".to_string())
+            })
+        })
+        .collect()
+}
+
+#[async_trait]
+impl Tool for DraftCheckTool {
+    fn name(&self) -> &str {
+        "draft_check"
+    }
+
+    fn description(&self) -> &str {
+        "Run deterministic consistency checks on a draft: capitalized terms that are never \
+         defined, defined terms that are unused or redefined, inconsistent party names, \
+         cross-references to sections that do not exist, section numbering gaps, double spaces, \
+         and repeated words. Pass a workspace 'path' or the draft 'text'. Fix reported errors \
+         before sending a draft out for review."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Workspace path of the draft, e.g. 'matters/acme-v-foo/drafts/spa.md'"
+                },
+                "text": {
+                    "type": "string",
+                    "description": "Draft text to check when it is not saved in the workspace"
+                },
+                "checks": {
+                    "type": "array",
+                    "items": { "type": "string", "enum": CHECKS },
+                    "description": "Checks to run (default: all)"
+                }
+            }
+        })
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: &JobContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let start = Instant::now();
+        let checks = requested_checks(&params)?;
+        let path = params
+            .get("path")
+            .and_then(|v| v.as_str())
+            .map(|p| p.trim().trim_matches('/'));
+        let text = params.get("text").and_then(|v| v.as_str());
+        let (content, matter_id) = match (path, text) {
+            (Some(_), Some(_)) => {
+                return Err(ToolError::InvalidParameters(
+                    "pass either 'path' or 'text', not both".to_string(),
+                ));
+            }
+            (Some(path), None) => self.read_draft(path, ctx).await?,
+            (None, Some(text)) => (text.to_string(), None),
+            (None, None) => {
+                return Err(ToolError::InvalidParameters(
+                    "pass 'path' to check a workspace draft or 'text' to check inline text"
+                        .to_string(),
+                ));
+            }
+        };
+
+        let report = check_draft(&content, &checks).map_err(ToolError::InvalidParameters)?;
+        let output = serde_json::json!({
+            "path": path,
+            "matter_id": matter_id,
+            "errors": report.count(IssueSeverity::Error),
+            "warnings": report.count(IssueSeverity::Warning),
+            "total_issues": report.issues.len(),
+            "defined_terms": report.defined_terms,
+            "sections": report.sections,
+            "issues": report.issues.iter().take(MAX_ISSUES).collect::<Vec<_>>(),
+        });
+        Ok(ToolOutput::success(output, start.elapsed()))
+    }
+
+    fn requires_sanitization(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DRAFT: &str = "\
+# Asset Purchase Agreement
+
+1. Sale. Seller shall sell the Assets (the \"Assets\") to Buyer.
+2. Price. The Purchase Price (the \"Purchase Price\") is due at the Closing Date.
+3. Notices. See Section 5 for  notice addresses.
+";
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn checks_workspace_drafts_and_inline_text() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", Arc::clone(&db)));
+        workspace
+            .write("matters/acme/drafts/apa.md", DRAFT)
+            .await
+            .expect("write draft");
+        let tool = DraftCheckTool::new(workspace);
+
+        let output = tool
+            .execute(
+                serde_json::json!({"path": "matters/acme/drafts/apa.md"}),
+                &JobContext::default(),
+            )
+            .await
+            .expect("check path");
+        let result = &output.result;
+        assert_eq!(result["matter_id"], "acme");
+        assert_eq!(
+            result["sections"],
+            serde_json::json!(["1", "2", "3"]),
+            "{result}"
+        );
+        let checks: Vec<&str> = result["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|issue| issue["check"].as_str().unwrap())
+            .collect();
+        assert!(checks.contains(&"undefined_term"), "{result}");
+        assert!(checks.contains(&"cross_reference"), "{result}");
+        assert!(checks.contains(&"double_space"), "{result}");
+
+        let output = tool
+            .execute(
+                serde_json::json!({"text": DRAFT, "checks": ["double_space"]}),
+                &JobContext::default(),
+            )
+            .await
+            .expect("check text");
+        assert_eq!(output.result["total_issues"], 1);
+        assert_eq!(output.result["issues"][0]["line"], 5);
+
+        let err = tool
+            .execute(
+                serde_json::json!({"text": DRAFT, "checks": ["spelling"]}),
+                &JobContext::default(),
+            )
+            .await
+            .expect_err("unknown check");
+        assert!(matches!(err, ToolError::InvalidParameters(_)), "{err}");
+    }
+}
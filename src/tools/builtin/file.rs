@@ -829,6 +829,7 @@ mod tests {
             conflict_check_enabled: true,
             conflict_file_fallback_enabled: false,
             conflict_reindex_on_startup: false,
+            supervised_mode: false,
             network: crate::config::LegalNetworkConfig {
                 deny_by_default: true,
                 allowed_domains: vec![],
@@ -850,6 +851,8 @@ mod tests {
                 exclude_from_search: true,
                 require_master_key_in_max_lockdown: true,
             },
+            storage: crate::config::LegalStorageConfig::default(),
+            llm_routing: crate::config::LegalLlmRoutingConfig::default(),
         };
 
         let tool = WriteFileTool::new()
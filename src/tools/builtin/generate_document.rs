@@ -0,0 +1,588 @@
+//! Guided document generation from matter templates.
+//!
+//! When a template declares required variables that have not been
+//! supplied, the tool returns the questions to ask instead of rendering,
+//! so the agent interviews the user before drafting.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::context::JobContext;
+use crate::db::{
+    CreateDocumentVersionParams, Database, DocumentReadinessState, DocumentTemplateRecord,
+    MatterDocumentCategory, RecordDocumentTemplateUseParams, UpsertMatterDocumentParams,
+};
+use crate::legal::docgen::{
+    TemplateVariable, TemplateVariableType, missing_required_variables, parse_variable_schema,
+    validate_variables,
+};
+use crate::legal::locale::Locale;
+use crate::tools::tool::{Tool, ToolError, ToolOutput, ToolRateLimitConfig, db_err};
+use crate::workspace::Workspace;
+
+/// Renders matter templates, asking for missing required variables first.
+pub struct GenerateDocumentTool {
+    workspace: Arc<Workspace>,
+    store: Arc<dyn Database>,
+    legal: Option<crate::config::LegalConfig>,
+}
+
+impl GenerateDocumentTool {
+    pub fn new(workspace: Arc<Workspace>, store: Arc<dyn Database>) -> Self {
+        Self {
+            workspace,
+            store,
+            legal: None,
+        }
+    }
+
+    pub fn with_legal_policy(mut self, legal: crate::config::LegalConfig) -> Self {
+        self.legal = Some(legal);
+        self
+    }
+
+    /// Templates usable for the matter with their variable schemas.
+    async fn list_templates(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+    ) -> Result<serde_json::Value, ToolError> {
+        let templates = self
+            .store
+            .list_document_templates(user_id, Some(matter_id))
+            .await
+            .map_err(db_err)?;
+        let templates: Vec<_> = templates
+            .into_iter()
+            .map(|template| {
+                serde_json::json!({
+                    "id": template.id.to_string(),
+                    "name": template.name,
+                    "shared": template.matter_id.is_none(),
+                    "variables": parse_variable_schema(&template.variables_json)
+                        .unwrap_or_default(),
+                })
+            })
+            .collect();
+        Ok(serde_json::json!({ "matter_id": matter_id, "templates": templates }))
+    }
+
+    /// Find a template by ID or name; matter templates win over shared ones.
+    async fn find_template(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        template: &str,
+    ) -> Result<DocumentTemplateRecord, ToolError> {
+        let found = match uuid::Uuid::parse_str(template) {
+            Ok(id) => self
+                .store
+                .get_document_template(user_id, id)
+                .await
+                .map_err(db_err)?,
+            Err(_) => self
+                .store
+                .get_document_template_by_name(user_id, Some(matter_id), template)
+                .await
+                .map_err(db_err)?,
+        };
+        found
+            .filter(|t| t.matter_id.as_deref().is_none_or(|m| m == matter_id))
+            .ok_or_else(|| {
+                ToolError::InvalidParameters(format!(
+                    "template '{template}' not found for matter '{matter_id}'"
+                ))
+            })
+    }
+
+    async fn generate(
+        &self,
+        ctx: &JobContext,
+        matter_id: &str,
+        template: DocumentTemplateRecord,
+        variables: &serde_json::Value,
+        display_name: Option<String>,
+        locale: Option<Locale>,
+    ) -> Result<serde_json::Value, ToolError> {
+        let matter = self
+            .store
+            .get_matter_db(&ctx.user_id, matter_id)
+            .await
+            .map_err(db_err)?
+            .ok_or_else(|| {
+                ToolError::InvalidParameters(format!("matter '{matter_id}' not found"))
+            })?;
+        let client = self
+            .store
+            .get_client(&ctx.user_id, matter.client_id)
+            .await
+            .map_err(db_err)?
+            .ok_or_else(|| {
+                ToolError::ExecutionFailed(format!("matter '{matter_id}' has no client record"))
+            })?;
+        let locale = match locale {
+            Some(locale) => locale,
+            None => crate::legal::client_comms::preferred_locale(
+                self.store.as_ref(),
+                &ctx.user_id,
+                client.id,
+            )
+            .await
+            .map_err(db_err)?,
+        };
+        let templates = self
+            .store
+            .list_document_templates(&ctx.user_id, Some(matter_id))
+            .await
+            .map_err(db_err)?;
+        let template = crate::legal::docgen::template_variant(&templates, &template, locale)
+            .cloned()
+            .unwrap_or(template);
+
+        let schema = parse_variable_schema(&template.variables_json).map_err(|e| {
+            ToolError::ExecutionFailed(format!("template variable schema is invalid: {e}"))
+        })?;
+
+        let missing = missing_required_variables(&schema, variables);
+        if !missing.is_empty() {
+            return Ok(needs_input(&template, &missing, &[]));
+        }
+        let extra = match validate_variables(&schema, variables) {
+            Ok(extra) => extra,
+            Err(problems) => {
+                let invalid: Vec<&TemplateVariable> = schema
+                    .iter()
+                    .filter(|v| {
+                        problems
+                            .iter()
+                            .any(|p| p.starts_with(&format!("'{}'", v.name)))
+                    })
+                    .collect();
+                return Ok(needs_input(&template, &invalid, &problems));
+            }
+        };
+
+        let mut partials = crate::legal::docgen::template_partials(&templates, template.id);
+        crate::legal::docgen::localize_partials(&mut partials, locale);
+        let parties = self
+            .store
+            .list_matter_parties(matter_id)
+            .await
+            .map_err(db_err)?;
+        let custom_field_schemas =
+            crate::legal::custom_fields::resolve_schemas(Some(&self.store), &ctx.user_id).await;
+        let custom_fields = crate::legal::custom_fields::fields_for_practice_area(
+            &custom_field_schemas,
+            matter.practice_area.as_deref(),
+        );
+        let mut context = crate::legal::docgen::build_context(&matter, &client, Some(&extra));
+        crate::legal::docgen::insert_caption_context(
+            &mut context,
+            &crate::legal::caption::matter_caption(&matter, &client, &parties, None),
+        );
+        crate::legal::docgen::insert_custom_field_context(
+            &mut context,
+            crate::legal::custom_fields::template_values(&custom_fields, &matter.custom_fields),
+        );
+        crate::legal::docgen::insert_locale_context(&mut context, locale);
+        let rendered = crate::legal::docgen::render_template_with_partials(
+            &template.body,
+            &context,
+            &partials,
+        )
+        .map_err(ToolError::ExecutionFailed)?;
+
+        let matter_prefix = format!(
+            "{}/{matter_id}",
+            crate::legal::policy::matter_root(self.legal.as_ref())
+        );
+        let timestamp = Utc::now().format("%Y%m%d-%H%M%S").to_string();
+        let path = crate::legal::docgen::generated_document_path(
+            &self.workspace,
+            &matter_prefix,
+            &template.name,
+            &timestamp,
+        )
+        .await
+        .map_err(db_err)?
+        .ok_or_else(|| {
+            ToolError::ExecutionFailed("no free path for the generated document".to_string())
+        })?;
+        let written = self
+            .workspace
+            .write(&path, &rendered)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Write failed: {e}")))?;
+        let linked = self
+            .store
+            .upsert_matter_document(
+                &ctx.user_id,
+                matter_id,
+                &UpsertMatterDocumentParams {
+                    memory_document_id: written.id,
+                    path: written.path.clone(),
+                    display_name: display_name.unwrap_or_else(|| template.name.clone()),
+                    category: MatterDocumentCategory::Internal,
+                    readiness_state: Some(DocumentReadinessState::Draft),
+                },
+            )
+            .await
+            .map_err(db_err)?;
+        self.store
+            .create_document_version(
+                &ctx.user_id,
+                &CreateDocumentVersionParams {
+                    matter_document_id: linked.id,
+                    label: "draft".to_string(),
+                    memory_document_id: written.id,
+                },
+            )
+            .await
+            .map_err(db_err)?;
+        if let Err(err) = self
+            .store
+            .record_document_template_use(
+                &ctx.user_id,
+                &RecordDocumentTemplateUseParams {
+                    template_id: template.id,
+                    matter_id: matter_id.to_string(),
+                    document_path: linked.path.clone(),
+                    used_by: ctx.user_id.clone(),
+                },
+            )
+            .await
+        {
+            tracing::warn!(template_id = %template.id, "Failed to record template use: {err}");
+        }
+
+        Ok(serde_json::json!({
+            "status": "generated",
+            "matter_document_id": linked.id.to_string(),
+            "path": linked.path,
+            "display_name": linked.display_name,
+            "template": template.name,
+            "locale": locale.code(),
+        }))
+    }
+}
+
+/// Output asking the agent to collect values before generating.
+fn needs_input(
+    template: &DocumentTemplateRecord,
+    variables: &[&TemplateVariable],
+    problems: &[String],
+) -> serde_json::Value {
+    let questions: Vec<_> = variables
+        .iter()
+        .map(|variable| {
+            serde_json::json!({
+                "name": variable.name,
+                "type": variable.var_type,
+                "question": question_for(variable),
+                "choices": variable.choices,
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "status": "needs_input",
+        "template": template.name,
+        "questions": questions,
+        "problems": problems,
+        "note": "Ask the user each question (one at a time if there are several), then call \
+                 generate_document again with every answer in 'variables'. Do not invent values.",
+    })
+}
+
+fn question_for(variable: &TemplateVariable) -> String {
+    let subject = variable
+        .description
+        .clone()
+        .unwrap_or_else(|| variable.name.replace('_', " "));
+    match variable.var_type {
+        TemplateVariableType::Enum => {
+            format!("{subject}? Options: {}", variable.choices.join(", "))
+        }
+        TemplateVariableType::Date => format!("{subject}? (YYYY-MM-DD)"),
+        TemplateVariableType::Boolean => format!("{subject}? (yes or no)"),
+        TemplateVariableType::List => format!("{subject}? (one per line)"),
+        _ => format!("{subject}?"),
+    }
+}
+
+#[async_trait]
+impl Tool for GenerateDocumentTool {
+    fn name(&self) -> &str {
+        "generate_document"
+    }
+
+    fn description(&self) -> &str {
+        "Draft a matter document from a template. With only 'matter_id', lists the templates \
+         available to the matter and the variables each one takes. With 'template', checks the \
+         supplied 'variables' against the template schema: if required values are missing or \
+         invalid it returns status 'needs_input' with questions to put to the user; otherwise \
+         it renders the template into the matter's drafts folder. Documents are rendered in \
+         'locale' (default: the client's preferred language), using the template's language \
+         variant such as 'engagement_letter.es.md' when one exists."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "matter_id": {
+                    "type": "string",
+                    "description": "Matter to draft the document for"
+                },
+                "template": {
+                    "type": "string",
+                    "description": "Template name or ID. Omit to list templates."
+                },
+                "variables": {
+                    "type": "object",
+                    "description": "Template variable values collected from the user, by variable name"
+                },
+                "display_name": {
+                    "type": "string",
+                    "description": "Name for the generated document (default: template name)"
+                },
+                "locale": {
+                    "type": "string",
+                    "description": "Language for the document, e.g. 'es' or 'en-GB' (default: the client's preferred language)"
+                }
+            },
+            "required": ["matter_id"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: &JobContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let start = Instant::now();
+        let raw_matter_id = params
+            .get("matter_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ToolError::InvalidParameters("matter_id is required".to_string()))?;
+        let matter_id = crate::legal::policy::sanitize_optional_matter_id(raw_matter_id)
+            .ok_or_else(|| ToolError::InvalidParameters("matter_id is empty".to_string()))?;
+        if let Some(scoped) = crate::legal::policy::matter_id_from_metadata(&ctx.metadata)
+            && scoped != matter_id
+        {
+            return Err(ToolError::NotAuthorized(format!(
+                "this job is scoped to matter '{scoped}'"
+            )));
+        }
+
+        let Some(template) = params
+            .get("template")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        else {
+            let output = self.list_templates(&ctx.user_id, &matter_id).await?;
+            return Ok(ToolOutput::success(output, start.elapsed()));
+        };
+        let variables = match params.get("variables") {
+            None | Some(serde_json::Value::Null) => serde_json::json!({}),
+            Some(value) if value.is_object() => value.clone(),
+            Some(_) => {
+                return Err(ToolError::InvalidParameters(
+                    "variables must be an object".to_string(),
+                ));
+            }
+        };
+        let display_name = params
+            .get("display_name")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string);
+        let locale =
+            match params
+                .get("locale")
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+            {
+                Some(raw) => Some(Locale::parse(raw).ok_or_else(|| {
+                    ToolError::InvalidParameters(format!("unknown locale '{raw}'"))
+                })?),
+                None => None,
+            };
+
+        let template = self
+            .find_template(&ctx.user_id, &matter_id, template)
+            .await?;
+        let output = self
+            .generate(ctx, &matter_id, template, &variables, display_name, locale)
+            .await?;
+        Ok(ToolOutput::success(output, start.elapsed()))
+    }
+
+    fn execution_timeout(&self) -> Duration {
+        Duration::from_secs(60)
+    }
+
+    fn requires_sanitization(&self) -> bool {
+        false
+    }
+
+    fn rate_limit_config(&self) -> Option<ToolRateLimitConfig> {
+        Some(ToolRateLimitConfig::new(20, 200))
+    }
+}
+
+#[cfg(all(test, feature = "libsql"))]
+mod tests {
+    use super::*;
+    use crate::db::{
+        ClientType, CreateClientParams, MatterStatus, UpsertDocumentTemplateParams,
+        UpsertMatterParams,
+    };
+
+    #[tokio::test]
+    async fn asks_for_missing_required_variables_before_rendering() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", Arc::clone(&db)));
+        let ctx = JobContext::default();
+        let client = db
+            .create_client(
+                &ctx.user_id,
+                &CreateClientParams {
+                    name: "Acme Corp".to_string(),
+                    client_type: ClientType::Entity,
+                    email: None,
+                    phone: None,
+                    address: None,
+                    notes: None,
+                },
+            )
+            .await
+            .expect("client");
+        db.upsert_matter(
+            &ctx.user_id,
+            &UpsertMatterParams {
+                matter_id: "demo".to_string(),
+                client_id: client.id,
+                status: MatterStatus::Active,
+                stage: None,
+                practice_area: None,
+                jurisdiction: None,
+                opened_at: None,
+                closed_at: None,
+                assigned_to: Vec::new(),
+                custom_fields: serde_json::json!({}),
+            },
+        )
+        .await
+        .expect("matter");
+        db.upsert_document_template(
+            &ctx.user_id,
+            &UpsertDocumentTemplateParams {
+                matter_id: Some("demo".to_string()),
+                name: "notice.md".to_string(),
+                body: "{{ client.name }} appears in {{ extra.court }} on {{ extra.hearing_date }}"
+                    .to_string(),
+                variables_json: serde_json::json!([
+                    {"name": "court", "type": "enum", "required": true, "choices": ["SDNY", "EDNY"]},
+                    {"name": "hearing_date", "type": "date", "required": true,
+                     "description": "Date of the hearing"},
+                ]),
+            },
+        )
+        .await
+        .expect("template");
+        let tool = GenerateDocumentTool::new(Arc::clone(&workspace), Arc::clone(&db));
+
+        let output = tool
+            .execute(
+                serde_json::json!({
+                    "matter_id": "demo",
+                    "template": "notice.md",
+                    "variables": {"court": "SDNY"},
+                }),
+                &ctx,
+            )
+            .await
+            .expect("interview");
+        assert_eq!(output.result["status"], "needs_input");
+        assert_eq!(output.result["questions"][0]["name"], "hearing_date");
+        assert_eq!(
+            output.result["questions"][0]["question"],
+            "Date of the hearing? (YYYY-MM-DD)"
+        );
+
+        let output = tool
+            .execute(
+                serde_json::json!({
+                    "matter_id": "demo",
+                    "template": "notice.md",
+                    "variables": {"court": "CDCA", "hearing_date": "2026-03-02"},
+                }),
+                &ctx,
+            )
+            .await
+            .expect("invalid choice");
+        assert_eq!(output.result["status"], "needs_input");
+        assert_eq!(output.result["questions"][0]["name"], "court");
+
+        let output = tool
+            .execute(
+                serde_json::json!({
+                    "matter_id": "demo",
+                    "template": "notice.md",
+                    "variables": {"court": "SDNY", "hearing_date": "2026-03-02"},
+                }),
+                &ctx,
+            )
+            .await
+            .expect("generate");
+        assert_eq!(output.result["status"], "generated");
+        let path = output.result["path"].as_str().expect("path");
+        assert!(path.starts_with("matters/demo/drafts/notice-"));
+        assert_eq!(
+            workspace.read(path).await.unwrap().content,
+            "Acme Corp appears in SDNY on 2026-03-02"
+        );
+
+        db.upsert_document_template(
+            &ctx.user_id,
+            &UpsertDocumentTemplateParams {
+                matter_id: None,
+                name: "notice.es.md".to_string(),
+                body: "{{ client.name }} comparece ante {{ extra.court }} el \
+                       {{ extra.hearing_date | legal_date }}"
+                    .to_string(),
+                variables_json: serde_json::json!([
+                    {"name": "court", "required": true},
+                    {"name": "hearing_date", "type": "date", "required": true},
+                ]),
+            },
+        )
+        .await
+        .expect("spanish variant");
+        let output = tool
+            .execute(
+                serde_json::json!({
+                    "matter_id": "demo",
+                    "template": "notice.md",
+                    "variables": {"court": "SDNY", "hearing_date": "2026-03-02"},
+                    "locale": "es-MX",
+                }),
+                &ctx,
+            )
+            .await
+            .expect("generate spanish");
+        assert_eq!(output.result["template"], "notice.es.md");
+        assert_eq!(output.result["locale"], "es");
+        let path = output.result["path"].as_str().expect("path");
+        assert_eq!(
+            workspace.read(path).await.unwrap().content,
+            "Acme Corp comparece ante SDNY el 2 de marzo de 2026"
+        );
+    }
+}
@@ -20,7 +20,7 @@ use crate::context::{ContextManager, JobContext, JobState};
 use crate::db::Database;
 use crate::history::SandboxJobRecord;
 use crate::orchestrator::auth::CredentialGrant;
-use crate::orchestrator::job_manager::{ContainerJobManager, JobMode};
+use crate::orchestrator::job_manager::{ContainerJobManager, JobAdmission, JobMode, JobPriority};
 use crate::secrets::SecretsStore;
 use crate::tools::tool::{ApprovalRequirement, Tool, ToolError, ToolOutput, require_str};
 
@@ -330,9 +330,38 @@ impl CreateJobTool {
             });
         }
 
+        // Chat turns run interactive; routine-dispatched jobs carry
+        // `job_priority: routine` in their metadata.
+        let priority = ctx
+            .metadata
+            .get("job_priority")
+            .and_then(|v| v.as_str())
+            .and_then(JobPriority::parse)
+            .unwrap_or_default();
+        if priority != JobPriority::Normal
+            && let Some(store) = self.store.clone()
+        {
+            tokio::spawn(async move {
+                if let Err(e) = store
+                    .update_sandbox_job_priority(job_id, priority.as_str())
+                    .await
+                {
+                    tracing::warn!(job_id = %job_id, "Failed to set job priority: {}", e);
+                }
+            });
+        }
+
         // Create the container job with the pre-determined job_id.
-        let _token = jm
-            .create_job(job_id, task, Some(project_dir), mode, credential_grants)
+        let admission = jm
+            .create_job(
+                job_id,
+                &ctx.user_id,
+                task,
+                Some(project_dir),
+                mode,
+                priority,
+                credential_grants,
+            )
             .await
             .map_err(|e| {
                 self.update_status(
@@ -346,9 +375,21 @@ impl CreateJobTool {
                 ToolError::ExecutionFailed(format!("failed to create container: {}", e))
             })?;
 
-        // Container started successfully.
-        let now = Utc::now();
-        self.update_status(job_id, "running", None, None, Some(now), None);
+        let queue_position = match admission {
+            JobAdmission::Started => {
+                self.update_status(job_id, "running", None, None, Some(Utc::now()), None);
+                None
+            }
+            // The job manager marks it running when a slot frees up.
+            JobAdmission::Queued { position } => {
+                self.update_status(job_id, "queued", None, None, None, None);
+                Some(position)
+            }
+            JobAdmission::Retrying { .. } => {
+                self.update_status(job_id, "queued", None, None, None, None);
+                jm.queue_position(job_id).await
+            }
+        };
 
         if !wait {
             // Spawn a background monitor that forwards Claude Code output
@@ -364,13 +405,23 @@ impl CreateJobTool {
                 crate::agent::job_monitor::spawn_job_monitor(job_id, etx.subscribe(), itx.clone());
             }
 
-            let result = serde_json::json!({
-                "job_id": job_id.to_string(),
-                "status": "started",
-                "message": "Container started. Use job_events to check status or job_prompt to send follow-up instructions.",
-                "project_dir": project_dir_str,
-                "browse_url": format!("/projects/{}", browse_id),
-            });
+            let result = match queue_position {
+                None => serde_json::json!({
+                    "job_id": job_id.to_string(),
+                    "status": "started",
+                    "message": "Container started. Use job_events to check status or job_prompt to send follow-up instructions.",
+                    "project_dir": project_dir_str,
+                    "browse_url": format!("/projects/{}", browse_id),
+                }),
+                Some(position) => serde_json::json!({
+                    "job_id": job_id.to_string(),
+                    "status": "queued",
+                    "queue_position": position,
+                    "message": "All sandbox slots are busy; the job will start when one frees up. Use job_status to check on it.",
+                    "project_dir": project_dir_str,
+                    "browse_url": format!("/projects/{}", browse_id),
+                }),
+            };
             return Ok(ToolOutput::success(result, start.elapsed()));
         }
 
@@ -399,7 +450,8 @@ impl CreateJobTool {
             match jm.get_handle(job_id).await {
                 Some(handle) => match handle.state {
                     crate::orchestrator::job_manager::ContainerState::Running
-                    | crate::orchestrator::job_manager::ContainerState::Creating => {
+                    | crate::orchestrator::job_manager::ContainerState::Creating
+                    | crate::orchestrator::job_manager::ContainerState::Queued => {
                         tokio::time::sleep(poll_interval).await;
                     }
                     crate::orchestrator::job_manager::ContainerState::Stopped => {
@@ -415,16 +467,9 @@ impl CreateJobTool {
                             .unwrap_or(true);
                         jm.cleanup_job(job_id).await;
 
-                        let finished_at = Utc::now();
+                        // The job manager has already recorded the final
+                        // status (completed, failed, or dead_letter).
                         if success {
-                            self.update_status(
-                                job_id,
-                                "completed",
-                                Some(true),
-                                None,
-                                None,
-                                Some(finished_at),
-                            );
                             let result = serde_json::json!({
                                 "job_id": job_id.to_string(),
                                 "status": "completed",
@@ -434,14 +479,6 @@ impl CreateJobTool {
                             });
                             return Ok(ToolOutput::success(result, start.elapsed()));
                         } else {
-                            self.update_status(
-                                job_id,
-                                "failed",
-                                Some(false),
-                                Some(message.clone()),
-                                None,
-                                Some(finished_at),
-                            );
                             return Err(ToolError::ExecutionFailed(format!(
                                 "container job failed: {}",
                                 message
@@ -17,8 +17,9 @@ use std::sync::Arc;
 use async_trait::async_trait;
 
 use crate::context::JobContext;
+use crate::error::WorkspaceError;
 use crate::tools::tool::{Tool, ToolError, ToolOutput, require_str};
-use crate::workspace::{Workspace, paths};
+use crate::workspace::{SearchConfig, Workspace, paths};
 
 /// Identity files that the LLM must not overwrite via tool calls.
 /// These are loaded into the system prompt and could be used for prompt
@@ -52,19 +53,63 @@ fn is_protected_identity_path(path: &str) -> bool {
         .any(|protected| normalized.eq_ignore_ascii_case(protected))
 }
 
+/// Resolve the active matter for a tool invocation.
+///
+/// Job/chat metadata wins over the static config: an explicit `matter_id`
+/// first, then `active_matter` (where `null` means "cleared"), and only when
+/// neither key is present the configured default.
+pub(crate) fn active_matter_for_ctx(
+    legal: &crate::config::LegalConfig,
+    ctx: &JobContext,
+) -> Option<String> {
+    if let Some(matter_id) = crate::legal::policy::matter_id_from_metadata(&ctx.metadata) {
+        return Some(matter_id);
+    }
+    match ctx.metadata.get("active_matter") {
+        Some(serde_json::Value::String(raw)) => {
+            crate::legal::policy::sanitize_optional_matter_id(raw)
+        }
+        Some(_) => None,
+        None => legal
+            .active_matter
+            .as_deref()
+            .and_then(crate::legal::policy::sanitize_optional_matter_id),
+    }
+}
+
 /// Tool for searching workspace memory.
 ///
 /// Performs hybrid search (FTS + semantic) across all memory documents.
 /// The agent should call this tool before answering questions about
 /// prior work, decisions, preferences, or any historical context.
+///
+/// With legal mode enabled and an active matter set, results are restricted
+/// to that matter's directory so other matters' files never leak into the
+/// session.
 pub struct MemorySearchTool {
     workspace: Arc<Workspace>,
+    legal: Option<crate::config::LegalConfig>,
 }
 
 impl MemorySearchTool {
     /// Create a new memory search tool.
     pub fn new(workspace: Arc<Workspace>) -> Self {
-        Self { workspace }
+        Self {
+            workspace,
+            legal: None,
+        }
+    }
+
+    pub fn with_legal_policy(mut self, legal: crate::config::LegalConfig) -> Self {
+        self.legal = Some(legal);
+        self
+    }
+
+    /// Matter directory this invocation is scoped to, if any.
+    fn matter_scope(&self, ctx: &JobContext) -> Option<String> {
+        let legal = self.legal.as_ref().filter(|l| l.enabled)?;
+        let matter_id = active_matter_for_ctx(legal, ctx)?;
+        Some(crate::legal::matter::matter_prefix(legal, &matter_id))
     }
 }
 
@@ -77,7 +122,9 @@ impl Tool for MemorySearchTool {
     fn description(&self) -> &str {
         "Search past memories, decisions, and context. MUST be called before answering \
          questions about prior work, decisions, dates, people, preferences, or todos. \
-         Returns relevant snippets with relevance scores."
+         Returns relevant snippets with relevance scores. When an answer relies on a \
+         snippet, cite it by copying its `anchor` (e.g. [[chunk:<id>]]) verbatim after \
+         the supported statement."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -103,7 +150,7 @@ impl Tool for MemorySearchTool {
     async fn execute(
         &self,
         params: serde_json::Value,
-        _ctx: &JobContext,
+        ctx: &JobContext,
     ) -> Result<ToolOutput, ToolError> {
         let start = std::time::Instant::now();
 
@@ -115,18 +162,29 @@ impl Tool for MemorySearchTool {
             .unwrap_or(5)
             .min(20) as usize;
 
+        let matter_scope = self.matter_scope(ctx);
+        let mut config = SearchConfig::default().with_limit(limit);
+        if let Some(prefix) = matter_scope.as_deref() {
+            config = config.with_path_prefix(prefix);
+        }
+
         let results = self
             .workspace
-            .search(query, limit)
+            .search_with_config(query, config)
             .await
             .map_err(|e| ToolError::ExecutionFailed(format!("Search failed: {}", e)))?;
 
         let output = serde_json::json!({
             "query": query,
+            "matter_scope": matter_scope,
             "results": results.iter().map(|r| serde_json::json!({
                 "content": r.content,
                 "score": r.score,
                 "document_id": r.document_id.to_string(),
+                "path": r.path,
+                "chunk_id": r.chunk_id.to_string(),
+                "chunk_index": r.chunk_index,
+                "anchor": r.anchor(),
                 "is_hybrid_match": r.is_hybrid(),
             })).collect::<Vec<_>>(),
             "result_count": results.len(),
@@ -195,6 +253,10 @@ impl Tool for MemoryWriteTool {
                     "type": "boolean",
                     "description": "If true, append to existing content. If false, replace entirely.",
                     "default": true
+                },
+                "if_match": {
+                    "type": "string",
+                    "description": "ETag from memory_read. With append=false, the write is rejected if the file changed since it was read; re-read and merge before retrying."
                 }
             },
             "required": ["content"]
@@ -259,6 +321,12 @@ impl Tool for MemoryWriteTool {
             .get("append")
             .and_then(|v| v.as_bool())
             .unwrap_or(true);
+        let if_match = params.get("if_match").and_then(|v| v.as_str());
+        if if_match.is_some() && (append || target == "daily_log") {
+            return Err(ToolError::InvalidParameters(
+                "if_match only applies to replacing writes (append=false)".to_string(),
+            ));
+        }
 
         let path = match target {
             "memory" => {
@@ -273,9 +341,9 @@ impl Tool for MemoryWriteTool {
                         .map_err(|e| ToolError::ExecutionFailed(format!("Write failed: {}", e)))?;
                 } else {
                     self.workspace
-                        .write(&matter_memory_path, content)
+                        .write_if_match(&matter_memory_path, content, if_match)
                         .await
-                        .map_err(|e| ToolError::ExecutionFailed(format!("Write failed: {}", e)))?;
+                        .map_err(write_error)?;
                 }
                 matter_memory_path
             }
@@ -305,9 +373,9 @@ impl Tool for MemoryWriteTool {
                         .map_err(|e| ToolError::ExecutionFailed(format!("Write failed: {}", e)))?;
                 } else {
                     self.workspace
-                        .write(&heartbeat_path, content)
+                        .write_if_match(&heartbeat_path, content, if_match)
                         .await
-                        .map_err(|e| ToolError::ExecutionFailed(format!("Write failed: {}", e)))?;
+                        .map_err(write_error)?;
                 }
                 heartbeat_path
             }
@@ -351,9 +419,9 @@ impl Tool for MemoryWriteTool {
                         .map_err(|e| ToolError::ExecutionFailed(format!("Write failed: {}", e)))?;
                 } else {
                     self.workspace
-                        .write(&resolved_path, content)
+                        .write_if_match(&resolved_path, content, if_match)
                         .await
-                        .map_err(|e| ToolError::ExecutionFailed(format!("Write failed: {}", e)))?;
+                        .map_err(write_error)?;
                 }
                 resolved_path
             }
@@ -382,6 +450,19 @@ impl Tool for MemoryWriteTool {
     }
 }
 
+fn write_error(e: WorkspaceError) -> ToolError {
+    match e {
+        WorkspaceError::WriteConflict { current_etag } => ToolError::ExecutionFailed(format!(
+            "Write rejected: the file changed since it was read (current etag: {}). \
+             Re-read it with memory_read, merge your changes, and retry.",
+            current_etag
+                .as_deref()
+                .unwrap_or("none, the file was deleted")
+        )),
+        other => ToolError::ExecutionFailed(format!("Write failed: {}", other)),
+    }
+}
+
 /// Tool for reading workspace files.
 ///
 /// Use this to read the full content of any file in the workspace.
@@ -431,9 +512,9 @@ impl Tool for MemoryReadTool {
 
         let path = require_str(&params, "path")?;
 
-        let doc = self
+        let (doc, etag) = self
             .workspace
-            .read(path)
+            .read_with_etag(path)
             .await
             .map_err(|e| ToolError::ExecutionFailed(format!("Read failed: {}", e)))?;
 
@@ -442,6 +523,7 @@ impl Tool for MemoryReadTool {
             "content": doc.content,
             "word_count": doc.word_count(),
             "updated_at": doc.updated_at.to_rfc3339(),
+            "etag": etag,
         });
 
         Ok(ToolOutput::success(output, start.elapsed()))
@@ -745,4 +827,126 @@ mod libsql_tests {
             "writing conflicts.json via memory_write should invalidate and refresh cache"
         );
     }
+
+    #[tokio::test]
+    async fn memory_write_if_match_rejects_stale_etags() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+        workspace
+            .write("drafts/brief.md", "Draft v1")
+            .await
+            .expect("seed draft");
+        let ctx = JobContext::with_user("test-user", "test", "etag");
+
+        let read = MemoryReadTool::new(Arc::clone(&workspace))
+            .execute(serde_json::json!({"path": "drafts/brief.md"}), &ctx)
+            .await
+            .expect("read draft");
+        let etag = read.result["etag"].as_str().expect("etag").to_string();
+
+        let tool = MemoryWriteTool::new(Arc::clone(&workspace));
+        let write = |content: &'static str| {
+            serde_json::json!({
+                "content": content,
+                "target": "drafts/brief.md",
+                "append": false,
+                "if_match": etag,
+            })
+        };
+        tool.execute(write("Draft v2"), &ctx)
+            .await
+            .expect("write with current etag");
+        let err = tool
+            .execute(write("Draft v2 from a stale read"), &ctx)
+            .await
+            .expect_err("stale etag");
+        assert!(
+            err.to_string().contains("changed since it was read"),
+            "{err}"
+        );
+        assert_eq!(
+            workspace.read("drafts/brief.md").await.unwrap().content,
+            "Draft v2"
+        );
+    }
+
+    #[tokio::test]
+    async fn memory_search_scopes_results_to_active_matter() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+        workspace
+            .write("matters/acme/notes.md", "Privileged strategy for Acme")
+            .await
+            .expect("seed acme");
+        workspace
+            .write("matters/globex/notes.md", "Privileged strategy for Globex")
+            .await
+            .expect("seed globex");
+
+        let mut legal =
+            LegalConfig::resolve(&Settings::default()).expect("default legal config resolves");
+        legal.enabled = true;
+        legal.active_matter = None;
+        let tool = MemorySearchTool::new(Arc::clone(&workspace)).with_legal_policy(legal);
+
+        let mut ctx = JobContext::with_user("test-user", "chat", "scoped search");
+        ctx.metadata = serde_json::json!({ "active_matter": "acme" });
+        let output = tool
+            .execute(serde_json::json!({ "query": "strategy" }), &ctx)
+            .await
+            .expect("scoped search should succeed");
+        assert_eq!(output.result["matter_scope"], "matters/acme");
+        assert_eq!(output.result["result_count"], 1);
+        assert!(
+            output.result["results"][0]["content"]
+                .as_str()
+                .is_some_and(|content| content.contains("Acme"))
+        );
+
+        let unscoped_ctx = JobContext::with_user("test-user", "chat", "unscoped search");
+        let output = tool
+            .execute(serde_json::json!({ "query": "strategy" }), &unscoped_ctx)
+            .await
+            .expect("unscoped search should succeed");
+        assert!(output.result["matter_scope"].is_null());
+        assert_eq!(output.result["result_count"], 2);
+    }
+
+    #[tokio::test]
+    async fn memory_search_results_carry_resolvable_chunk_anchors() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("test-user", Arc::clone(&db)));
+        workspace
+            .write(
+                "matters/acme/lease.md",
+                "Rent is due on the first of each month",
+            )
+            .await
+            .expect("seed lease");
+        let tool = MemorySearchTool::new(Arc::clone(&workspace));
+
+        let ctx = JobContext::with_user("test-user", "chat", "cited search");
+        let output = tool
+            .execute(serde_json::json!({ "query": "rent" }), &ctx)
+            .await
+            .expect("search should succeed");
+        let hit = &output.result["results"][0];
+        assert_eq!(hit["path"], "matters/acme/lease.md");
+        assert_eq!(hit["chunk_index"], 0);
+        let anchor = hit["anchor"].as_str().expect("anchor");
+        let cited = crate::workspace::extract_chunk_anchors(anchor);
+        assert_eq!(cited.len(), 1);
+        assert_eq!(hit["chunk_id"], cited[0].to_string());
+
+        let chunk = workspace
+            .get_chunk(cited[0])
+            .await
+            .expect("lookup")
+            .expect("chunk resolves for its owner");
+        assert_eq!(chunk.path, "matters/acme/lease.md");
+        assert!(chunk.content.contains("Rent is due"));
+
+        let other = Workspace::new_with_db("other-user", Arc::clone(&db));
+        assert!(other.get_chunk(cited[0]).await.expect("lookup").is_none());
+    }
 }
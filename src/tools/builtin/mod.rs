@@ -1,31 +1,51 @@
 //! Built-in tools that come with the agent.
 
 pub mod canlii;
+pub mod chronology;
+pub mod compare_documents;
+mod compose_email;
 pub mod corporate_compliance;
 pub mod court_deadline;
+pub mod damages;
+mod document_tags;
+pub mod draft_check;
 mod echo;
 pub mod extension_tools;
 mod file;
+pub mod generate_document;
 mod http;
 mod job;
 mod json;
 mod memory;
 pub mod ontario_forms;
 pub mod ontario_limitation;
+pub mod plain_language;
+pub mod precedent;
 pub mod routine;
+mod run_code;
 pub(crate) mod shell;
 pub mod skill_tools;
+pub mod status_report;
 mod time;
+pub mod transcript;
+pub mod translation;
 pub mod trust_compliance;
 
 pub use canlii::CanLiiSearchTool;
+pub use chronology::ExtractMatterFactsTool;
+pub use compare_documents::CompareDocumentsTool;
+pub use compose_email::ComposeEmailTool;
 pub use corporate_compliance::CorporateComplianceCheckerTool;
 pub use court_deadline::{CourtDeadlineCalculatorTool, ListCourtRulesTool};
+pub use damages::DamagesCalculatorTool;
+pub use document_tags::TagDocumentTool;
+pub use draft_check::DraftCheckTool;
 pub use echo::EchoTool;
 pub use extension_tools::{
     ToolActivateTool, ToolAuthTool, ToolInstallTool, ToolListTool, ToolRemoveTool, ToolSearchTool,
 };
 pub use file::{ApplyPatchTool, ListDirTool, ReadFileTool, WriteFileTool};
+pub use generate_document::GenerateDocumentTool;
 pub use http::HttpTool;
 pub use job::{
     CancelJobTool, CreateJobTool, JobEventsTool, JobPromptTool, JobStatusTool, ListJobsTool,
@@ -35,12 +55,18 @@ pub use json::JsonTool;
 pub use memory::{MemoryReadTool, MemorySearchTool, MemoryTreeTool, MemoryWriteTool};
 pub use ontario_forms::OntarioCourtFormTool;
 pub use ontario_limitation::OntarioLimitationCalculatorTool;
+pub use plain_language::PlainLanguageReviewTool;
+pub use precedent::PrecedentSearchTool;
 pub use routine::{
     RoutineCreateTool, RoutineDeleteTool, RoutineHistoryTool, RoutineListTool, RoutineUpdateTool,
 };
+pub use run_code::RunCodeTool;
 pub use shell::ShellTool;
 pub use skill_tools::{SkillInstallTool, SkillListTool, SkillRemoveTool, SkillSearchTool};
+pub use status_report::ClientStatusReportTool;
 pub use time::TimeTool;
+pub use transcript::QuoteTestimonyTool;
+pub use translation::TranslateDocumentTool;
 pub use trust_compliance::TrustComplianceCheckerTool;
 
 mod html_converter;
@@ -0,0 +1,304 @@
+//! Readability scoring and plain-language rewrite tool for client letters.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::context::JobContext;
+use crate::db::Database;
+use crate::legal::readability::{measure, resolve_thresholds, rewrite_passage, score_document};
+use crate::llm::LlmProvider;
+use crate::tools::tool::{Tool, ToolError, ToolOutput, ToolRateLimitConfig};
+use crate::workspace::Workspace;
+
+/// Most flagged passages rewritten in one call.
+const MAX_REWRITES: usize = 8;
+
+/// Scores a client-facing document for readability and legalese, and
+/// optionally proposes plain-language rewrites of flagged passages.
+pub struct PlainLanguageReviewTool {
+    workspace: Arc<Workspace>,
+    llm: Arc<dyn LlmProvider>,
+    store: Option<Arc<dyn Database>>,
+    legal: Option<crate::config::LegalConfig>,
+}
+
+impl PlainLanguageReviewTool {
+    pub fn new(
+        workspace: Arc<Workspace>,
+        llm: Arc<dyn LlmProvider>,
+        store: Option<Arc<dyn Database>>,
+    ) -> Self {
+        Self {
+            workspace,
+            llm,
+            store,
+            legal: None,
+        }
+    }
+
+    pub fn with_legal_policy(mut self, legal: crate::config::LegalConfig) -> Self {
+        self.legal = Some(legal);
+        self
+    }
+
+    /// Read a workspace document, refusing files from a matter other than
+    /// the active one.
+    async fn read_document(
+        &self,
+        path: &str,
+        ctx: &JobContext,
+    ) -> Result<(String, Option<String>), ToolError> {
+        if path.is_empty() {
+            return Err(ToolError::InvalidParameters(
+                "path must not be empty".to_string(),
+            ));
+        }
+        if path.split('/').any(|part| part == "..") {
+            return Err(ToolError::InvalidParameters(
+                "path must not contain '..' segments".to_string(),
+            ));
+        }
+        let matter_id = crate::legal::workspace_crypto::matter_id_for_path(
+            path,
+            crate::legal::policy::matter_root(self.legal.as_ref()),
+        );
+        if let Some(matter_id) = matter_id.as_deref()
+            && let Some(legal) = self.legal.as_ref().filter(|l| l.enabled)
+            && let Some(active) = super::memory::active_matter_for_ctx(legal, ctx)
+            && active != matter_id
+        {
+            return Err(ToolError::NotAuthorized(format!(
+                "document belongs to matter '{matter_id}' but the active matter is '{active}'"
+            )));
+        }
+        let doc = self
+            .workspace
+            .read(path)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Read failed: {e}")))?;
+        Ok((doc.content, matter_id))
+    }
+}
+
+fn optional_threshold(params: &serde_json::Value, name: &str) -> Result<Option<f64>, ToolError> {
+    match params.get(name).filter(|v| !v.is_null()) {
+        None => Ok(None),
+        Some(value) => value
+            .as_f64()
+            .map(Some)
+            .ok_or_else(|| ToolError::InvalidParameters(format!("{name} must be a number"))),
+    }
+}
+
+#[async_trait]
+impl Tool for PlainLanguageReviewTool {
+    fn name(&self) -> &str {
+        "plain_language_review"
+    }
+
+    fn description(&self) -> &str {
+        "Score a client-facing document (letter, email, status report) for readability: \
+         Flesch reading ease, Flesch-Kincaid grade level, and legalese with plain alternatives. \
+         Passages over the firm's grade or legalese thresholds are flagged. With 'rewrite', \
+         proposes a plain-language version of each flagged passage and re-scores it. \
+         Proposals are suggestions: show them to the lawyer and only edit the document once \
+         they approve."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Workspace path of the document, e.g. 'matters/acme-v-foo/drafts/client-letter.md'"
+                },
+                "text": {
+                    "type": "string",
+                    "description": "Document text to score when it is not saved in the workspace"
+                },
+                "rewrite": {
+                    "type": "boolean",
+                    "description": "Propose plain-language rewrites of flagged passages (default false)"
+                },
+                "max_grade": {
+                    "type": "number",
+                    "description": "Override the firm's maximum grade level for this review"
+                },
+                "max_legalese_per_100_words": {
+                    "type": "number",
+                    "description": "Override the firm's maximum legalese density for this review"
+                }
+            }
+        })
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: &JobContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let start = Instant::now();
+        let path = params
+            .get("path")
+            .and_then(|v| v.as_str())
+            .map(|p| p.trim().trim_matches('/'));
+        let text = params.get("text").and_then(|v| v.as_str());
+        let rewrite = params
+            .get("rewrite")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let mut thresholds = resolve_thresholds(self.store.as_ref(), &ctx.user_id).await;
+        if let Some(max_grade) = optional_threshold(&params, "max_grade")? {
+            thresholds.max_grade = max_grade;
+        }
+        if let Some(max_legalese) = optional_threshold(&params, "max_legalese_per_100_words")? {
+            thresholds.max_legalese_per_100_words = max_legalese;
+        }
+        thresholds
+            .validate()
+            .map_err(ToolError::InvalidParameters)?;
+
+        let (content, matter_id) = match (path, text) {
+            (Some(_), Some(_)) => {
+                return Err(ToolError::InvalidParameters(
+                    "pass either 'path' or 'text', not both".to_string(),
+                ));
+            }
+            (Some(path), None) => self.read_document(path, ctx).await?,
+            (None, Some(text)) => (text.to_string(), None),
+            (None, None) => {
+                return Err(ToolError::InvalidParameters(
+                    "pass 'path' to score a workspace document or 'text' to score inline text"
+                        .to_string(),
+                ));
+            }
+        };
+
+        let report = score_document(&content, &thresholds);
+        let mut output = serde_json::json!({
+            "path": path,
+            "matter_id": matter_id,
+            "thresholds": report.thresholds,
+            "within_thresholds": report.within_thresholds,
+            "metrics": report.metrics,
+            "passages": report.passages,
+            "flagged": report.flagged,
+            "legalese_summary": report.legalese_summary,
+        });
+
+        if rewrite {
+            let mut proposals = Vec::new();
+            for passage in report.flagged.iter().take(MAX_REWRITES) {
+                let proposed = rewrite_passage(self.llm.as_ref(), passage, thresholds.max_grade)
+                    .await
+                    .map_err(|e| ToolError::ExecutionFailed(format!("Rewrite failed: {e}")))?;
+                let after = measure(&proposed);
+                proposals.push(serde_json::json!({
+                    "line": passage.line,
+                    "original": passage.text,
+                    "proposed": proposed,
+                    "grade_before": passage.metrics.grade_level,
+                    "grade_after": after.grade_level,
+                    "legalese_after": after.legalese,
+                    "within_thresholds": after.grade_level <= thresholds.max_grade
+                        && after.legalese_per_100_words <= thresholds.max_legalese_per_100_words,
+                }));
+            }
+            output["proposals"] = serde_json::json!(proposals);
+            output["proposals_truncated"] = serde_json::json!(report.flagged.len() > MAX_REWRITES);
+        }
+        Ok(ToolOutput::success(output, start.elapsed()))
+    }
+
+    fn execution_timeout(&self) -> Duration {
+        // Rewrites are one sequential LLM call per flagged passage.
+        Duration::from_secs(300)
+    }
+
+    fn requires_sanitization(&self) -> bool {
+        false
+    }
+
+    fn rate_limit_config(&self) -> Option<ToolRateLimitConfig> {
+        Some(ToolRateLimitConfig::new(20, 200))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::StubLlm;
+
+    const LETTER: &str = "\
+Dear Ms. Roe,
+
+Pursuant to the aforementioned agreement, and notwithstanding any prior
+correspondence, the undersigned hereby requests remittance of the outstanding
+consideration forthwith.
+
+Thank you for your help.
+";
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn flags_legalese_and_proposes_rewrites_with_setting_thresholds() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", Arc::clone(&db)));
+        workspace
+            .write("matters/acme/drafts/letter.md", LETTER)
+            .await
+            .expect("write letter");
+        let llm = Arc::new(StubLlm::new("Please pay the amount you owe us now."));
+        let tool = PlainLanguageReviewTool::new(
+            workspace,
+            Arc::clone(&llm) as Arc<dyn LlmProvider>,
+            Some(Arc::clone(&db)),
+        );
+        let ctx = JobContext::default();
+
+        let output = tool
+            .execute(
+                serde_json::json!({"path": "matters/acme/drafts/letter.md", "rewrite": true}),
+                &ctx,
+            )
+            .await
+            .expect("review");
+        let result = &output.result;
+        assert_eq!(result["matter_id"], "acme");
+        assert_eq!(result["within_thresholds"], false);
+        assert_eq!(result["flagged"].as_array().map(Vec::len), Some(1));
+        assert_eq!(result["flagged"][0]["line"], 3);
+        let proposals = result["proposals"].as_array().unwrap();
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(
+            proposals[0]["proposed"],
+            "Please pay the amount you owe us now."
+        );
+        assert_eq!(proposals[0]["within_thresholds"], true);
+        assert_eq!(llm.calls(), 1);
+
+        db.set_setting(
+            &ctx.user_id,
+            crate::legal::readability::PLAIN_LANGUAGE_SETTING_KEY,
+            &serde_json::json!({"max_grade": 20, "max_legalese_per_100_words": 100}),
+        )
+        .await
+        .expect("save thresholds");
+        let output = tool
+            .execute(serde_json::json!({"text": LETTER}), &ctx)
+            .await
+            .expect("review text");
+        assert_eq!(output.result["thresholds"]["max_grade"], 20.0);
+        assert!(output.result.get("proposals").is_none());
+
+        let err = tool
+            .execute(serde_json::json!({"text": LETTER, "max_grade": 0}), &ctx)
+            .await
+            .expect_err("invalid override");
+        assert!(matches!(err, ToolError::InvalidParameters(_)), "{err}");
+    }
+}
@@ -0,0 +1,129 @@
+//! Firm precedent retrieval tool.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+
+use crate::context::JobContext;
+use crate::db::Database;
+use crate::legal::precedent::{query_terms, suggest_precedents};
+use crate::tools::tool::{Tool, ToolError, ToolOutput, require_str};
+use crate::workspace::Workspace;
+
+const DEFAULT_LIMIT: usize = 5;
+const MAX_LIMIT: usize = 20;
+
+/// Searches the firm precedent bank for reusable work product, returning
+/// similarity scores and the matter each precedent came from.
+pub struct PrecedentSearchTool {
+    workspace: Arc<Workspace>,
+    store: Arc<dyn Database>,
+    legal: Option<crate::config::LegalConfig>,
+}
+
+impl PrecedentSearchTool {
+    pub fn new(workspace: Arc<Workspace>, store: Arc<dyn Database>) -> Self {
+        Self {
+            workspace,
+            store,
+            legal: None,
+        }
+    }
+
+    pub fn with_legal_policy(mut self, legal: crate::config::LegalConfig) -> Self {
+        self.legal = Some(legal);
+        self
+    }
+}
+
+#[async_trait]
+impl Tool for PrecedentSearchTool {
+    fn name(&self) -> &str {
+        "precedent_search"
+    }
+
+    fn description(&self) -> &str {
+        "Search the firm precedent bank (anonymized work product from closed matters) before \
+         drafting. Returns the best matches with a similarity score (share of query terms \
+         covered), an excerpt, and the source matter for attribution. Precedent from matters \
+         the user is walled off from is never returned. Use a few key terms, e.g. \
+         'indemnification cap' or 'non-compete employee'. Adapt precedent to the new matter; \
+         never copy placeholders like [Client] into a final draft."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Key terms for the clause or document being drafted"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Most suggestions to return (default 5, max 20)"
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: &JobContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let start = Instant::now();
+        let query = require_str(&params, "query")?;
+        if query_terms(query).is_empty() {
+            return Err(ToolError::InvalidParameters(
+                "query must contain at least one search term".to_string(),
+            ));
+        }
+        let limit = params
+            .get("limit")
+            .and_then(|v| v.as_u64())
+            .map(|n| n as usize)
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT);
+
+        // Rank precedent from the drafting matter's practice area first on ties.
+        let active_matter = self
+            .legal
+            .as_ref()
+            .filter(|legal| legal.enabled)
+            .and_then(|legal| super::memory::active_matter_for_ctx(legal, ctx));
+        let practice_area = match active_matter.as_deref() {
+            Some(matter_id) => self
+                .store
+                .get_matter_db(&ctx.user_id, matter_id)
+                .await
+                .map_err(|e| ToolError::ExecutionFailed(format!("Matter lookup failed: {e}")))?
+                .and_then(|matter| matter.practice_area),
+            None => None,
+        };
+
+        let suggestions = suggest_precedents(
+            self.store.as_ref(),
+            self.workspace.as_ref(),
+            &ctx.user_id,
+            &ctx.user_id,
+            query,
+            practice_area.as_deref(),
+            limit,
+        )
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(format!("Precedent search failed: {e}")))?;
+        let output = serde_json::json!({
+            "query": query,
+            "matter_id": active_matter,
+            "suggestions": suggestions,
+        });
+        Ok(ToolOutput::success(output, start.elapsed()))
+    }
+
+    fn requires_sanitization(&self) -> bool {
+        false
+    }
+}
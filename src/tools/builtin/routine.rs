@@ -14,8 +14,10 @@ use async_trait::async_trait;
 use chrono::Utc;
 use uuid::Uuid;
 
+use crate::agent::execution_window::ExecutionOverride;
 use crate::agent::routine::{
-    NotifyConfig, Routine, RoutineAction, RoutineGuardrails, Trigger, next_cron_fire,
+    NotifyConfig, Routine, RoutineAction, RoutineChain, RoutineGuardrails, Trigger, check_chain,
+    default_report_period_days,
 };
 use crate::agent::routine_engine::RoutineEngine;
 use crate::context::JobContext;
@@ -66,7 +68,15 @@ impl Tool for RoutineCreateTool {
                 },
                 "schedule": {
                     "type": "string",
-                    "description": "Cron expression (for cron trigger). E.g. '0 9 * * MON-FRI' for weekdays at 9am. Uses 6-field cron (sec min hour day month weekday)."
+                    "description": "Schedule for cron trigger: 6-field cron (sec min hour day month weekday, e.g. '0 0 9 * * MON-FRI'), 5-field cron, or plain English like 'every weekday at 9am', 'every 2h', 'monthly on the 1st at 8am'."
+                },
+                "timezone": {
+                    "type": "string",
+                    "description": "IANA timezone the schedule is read in, e.g. 'America/New_York' (cron trigger; default: UTC)"
+                },
+                "jitter_secs": {
+                    "type": "integer",
+                    "description": "Random delay of up to this many seconds added to each cron fire (max 3600; default: 0)"
                 },
                 "event_pattern": {
                     "type": "string",
@@ -87,12 +97,36 @@ impl Tool for RoutineCreateTool {
                 },
                 "action_type": {
                     "type": "string",
-                    "enum": ["lightweight", "full_job"],
-                    "description": "Execution mode: 'lightweight' (single LLM call, default) or 'full_job' (multi-turn with tools)"
+                    "enum": ["lightweight", "full_job", "matter_status_report"],
+                    "description": "Execution mode: 'lightweight' (single LLM call, default), 'full_job' (multi-turn with tools), or 'matter_status_report' (compiles a client status memo from matter records into the matter's exports/ folder; the prompt becomes the memo's opening note)"
+                },
+                "matter_id": {
+                    "type": "string",
+                    "description": "Matter to report on (matter_status_report only; default: every active matter)"
+                },
+                "period_days": {
+                    "type": "integer",
+                    "description": "Days of activity each memo covers (matter_status_report only; default: 7, max: 90)"
+                },
+                "send": {
+                    "type": "boolean",
+                    "description": "Queue each memo for attorney approval, after which it is emailed to the client (matter_status_report only; default: false)"
                 },
                 "cooldown_secs": {
                     "type": "integer",
                     "description": "Minimum seconds between fires (default: 300)"
+                },
+                "urgent": {
+                    "type": "boolean",
+                    "description": "Keep running when the active matter's monthly spend cap is exhausted (default: false)"
+                },
+                "execution": {
+                    "type": "object",
+                    "description": "Override the firm's execution windows, e.g. {\"windows\": [{\"days\": [\"mon\", \"tue\", \"wed\", \"thu\", \"fri\"], \"start\": \"09:00\", \"end\": \"17:00\"}], \"ignore_blackouts\": false}. An empty windows list allows any time (default: firm windows)"
+                },
+                "chain": {
+                    "type": "object",
+                    "description": "Routines to fire when this one finishes, by name, e.g. {\"on_success\": [\"digest\"], \"on_condition\": [{\"routine\": \"compute-deadlines\", \"status\": [\"attention\"], \"pattern\": \"new order\"}]}. on_success fires after ok or attention runs; a condition matches its statuses (default ok and attention) and a case-insensitive regex on the run summary. Targets must exist and may not lead back to this routine"
                 }
             },
             "required": ["name", "trigger_type", "prompt"]
@@ -129,13 +163,15 @@ impl Tool for RoutineCreateTool {
                                 "cron trigger requires 'schedule'".to_string(),
                             )
                         })?;
-                // Validate cron expression
-                next_cron_fire(schedule).map_err(|e| {
-                    ToolError::InvalidParameters(format!("invalid cron schedule: {e}"))
-                })?;
-                Trigger::Cron {
-                    schedule: schedule.to_string(),
-                }
+                Trigger::cron_from_input(
+                    schedule,
+                    params.get("timezone").and_then(|v| v.as_str()),
+                    params
+                        .get("jitter_secs")
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(0),
+                )
+                .map_err(|e| ToolError::InvalidParameters(format!("invalid cron schedule: {e}")))?
             }
             "event" => {
                 let pattern = params
@@ -160,7 +196,7 @@ impl Tool for RoutineCreateTool {
             }
             "webhook" => Trigger::Webhook {
                 path: None,
-                secret: None,
+                secret: Some(crate::agent::routine::generate_webhook_token()),
             },
             "manual" => Trigger::Manual,
             other => {
@@ -197,6 +233,30 @@ impl Tool for RoutineCreateTool {
                 description: prompt.to_string(),
                 max_iterations: 10,
             },
+            "matter_status_report" => {
+                let period_days = match params.get("period_days").and_then(|v| v.as_u64()) {
+                    None => default_report_period_days(),
+                    Some(days @ 1..=90) => days as u32,
+                    Some(_) => {
+                        return Err(ToolError::InvalidParameters(
+                            "period_days must be between 1 and 90".to_string(),
+                        ));
+                    }
+                };
+                let prompt = prompt.trim();
+                RoutineAction::MatterStatusReport {
+                    matter_id: params
+                        .get("matter_id")
+                        .and_then(|v| v.as_str())
+                        .and_then(crate::legal::policy::sanitize_optional_matter_id),
+                    note: (!prompt.is_empty()).then(|| prompt.to_string()),
+                    period_days,
+                    send: params
+                        .get("send")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false),
+                }
+            }
             other => {
                 return Err(ToolError::InvalidParameters(format!(
                     "unknown action_type: {other}"
@@ -208,13 +268,19 @@ impl Tool for RoutineCreateTool {
             .get("cooldown_secs")
             .and_then(|v| v.as_u64())
             .unwrap_or(300);
+        let urgent = params
+            .get("urgent")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let execution: ExecutionOverride = match params.get("execution") {
+            Some(value) if !value.is_null() => serde_json::from_value(value.clone())
+                .map_err(|e| ToolError::InvalidParameters(format!("invalid execution: {e}")))?,
+            _ => ExecutionOverride::default(),
+        };
+        let chain = parse_chain(&params)?.unwrap_or_default();
 
         // Compute next fire time for cron
-        let next_fire = if let Trigger::Cron { ref schedule } = trigger {
-            next_cron_fire(schedule).unwrap_or(None)
-        } else {
-            None
-        };
+        let next_fire = trigger.next_fire().unwrap_or(None);
 
         let routine = Routine {
             id: Uuid::new_v4(),
@@ -228,8 +294,11 @@ impl Tool for RoutineCreateTool {
                 cooldown: Duration::from_secs(cooldown_secs),
                 max_concurrent: 1,
                 dedup_window: None,
+                urgent,
+                execution,
             },
             notify: NotifyConfig::default(),
+            chain,
             last_run_at: None,
             next_fire_at: next_fire,
             run_count: 0,
@@ -238,6 +307,7 @@ impl Tool for RoutineCreateTool {
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
+        check_chain_against_store(self.store.as_ref(), &routine).await?;
 
         self.store
             .create_routine(&routine)
@@ -265,6 +335,31 @@ impl Tool for RoutineCreateTool {
     }
 }
 
+/// The optional `chain` parameter of routine_create and routine_update.
+fn parse_chain(params: &serde_json::Value) -> Result<Option<RoutineChain>, ToolError> {
+    match params.get("chain") {
+        Some(value) if !value.is_null() => serde_json::from_value(value.clone())
+            .map(Some)
+            .map_err(|e| ToolError::InvalidParameters(format!("invalid chain: {e}"))),
+        _ => Ok(None),
+    }
+}
+
+/// Reject chains naming unknown routines or looping back to `routine`.
+async fn check_chain_against_store(
+    store: &dyn Database,
+    routine: &Routine,
+) -> Result<(), ToolError> {
+    if routine.chain.is_empty() {
+        return Ok(());
+    }
+    let existing = store
+        .list_routines(&routine.user_id)
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(format!("DB error: {e}")))?;
+    check_chain(routine, &existing).map_err(|e| ToolError::InvalidParameters(e.to_string()))
+}
+
 // ==================== routine_list ====================
 
 pub struct RoutineListTool {
@@ -359,7 +454,7 @@ impl Tool for RoutineUpdateTool {
     }
 
     fn description(&self) -> &str {
-        "Update an existing routine. Can modify trigger, prompt, schedule, or toggle enabled state. \
+        "Update an existing routine. Can modify trigger, prompt, schedule, chain, or toggle enabled state. \
          Pass the routine name and only the fields you want to change."
     }
 
@@ -381,11 +476,23 @@ impl Tool for RoutineUpdateTool {
                 },
                 "schedule": {
                     "type": "string",
-                    "description": "New cron schedule (for cron triggers)"
+                    "description": "New schedule for cron triggers (cron or plain English, e.g. 'every weekday at 9am')"
+                },
+                "timezone": {
+                    "type": "string",
+                    "description": "New IANA timezone for cron triggers"
+                },
+                "jitter_secs": {
+                    "type": "integer",
+                    "description": "New maximum random delay in seconds for cron triggers"
                 },
                 "description": {
                     "type": "string",
                     "description": "New description"
+                },
+                "chain": {
+                    "type": "object",
+                    "description": "Replacement chain of follow-up routines ({\"on_success\": [...], \"on_condition\": [...]}, same shape as routine_create); {} removes it"
                 }
             },
             "required": ["name"]
@@ -421,18 +528,44 @@ impl Tool for RoutineUpdateTool {
             match &mut routine.action {
                 RoutineAction::Lightweight { prompt: p, .. } => *p = prompt.to_string(),
                 RoutineAction::FullJob { description: d, .. } => *d = prompt.to_string(),
+                RoutineAction::MatterStatusReport { note, .. } => {
+                    *note = Some(prompt.trim().to_string()).filter(|n| !n.is_empty())
+                }
             }
         }
 
-        if let Some(schedule) = params.get("schedule").and_then(|v| v.as_str()) {
-            // Validate
-            next_cron_fire(schedule)
-                .map_err(|e| ToolError::InvalidParameters(format!("invalid cron schedule: {e}")))?;
-
-            routine.trigger = Trigger::Cron {
-                schedule: schedule.to_string(),
+        let schedule = params.get("schedule").and_then(|v| v.as_str());
+        let timezone = params.get("timezone").and_then(|v| v.as_str());
+        let jitter_secs = params.get("jitter_secs").and_then(|v| v.as_u64());
+        if schedule.is_some() || timezone.is_some() || jitter_secs.is_some() {
+            let (current_schedule, current_timezone, current_jitter) = match &routine.trigger {
+                Trigger::Cron {
+                    schedule,
+                    timezone,
+                    jitter_secs,
+                } => (Some(schedule.clone()), timezone.clone(), *jitter_secs),
+                _ => (None, None, 0),
             };
-            routine.next_fire_at = next_cron_fire(schedule).unwrap_or(None);
+            let schedule = schedule
+                .map(String::from)
+                .or(current_schedule)
+                .ok_or_else(|| {
+                    ToolError::InvalidParameters(
+                        "'timezone' and 'jitter_secs' only apply to cron routines".to_string(),
+                    )
+                })?;
+            routine.trigger = Trigger::cron_from_input(
+                &schedule,
+                timezone.or(current_timezone.as_deref()),
+                jitter_secs.unwrap_or(current_jitter),
+            )
+            .map_err(|e| ToolError::InvalidParameters(format!("invalid cron schedule: {e}")))?;
+            routine.next_fire_at = routine.trigger.next_fire().unwrap_or(None);
+        }
+
+        if let Some(chain) = parse_chain(&params)? {
+            routine.chain = chain;
+            check_chain_against_store(self.store.as_ref(), &routine).await?;
         }
 
         self.store
@@ -0,0 +1,598 @@
+//! Short-script execution for data wrangling (damages spreadsheets,
+//! discovery exports, etc.).
+//!
+//! Scripts never touch the host or the live workspace. Requested workspace
+//! documents are copied into a staging directory that the container sees
+//! read-only; whatever the script writes to `outputs/` is copied back into
+//! the workspace afterwards. The container itself is started through
+//! [`ContainerJobManager::run_script`] with no network, a read-only root
+//! filesystem, and memory/time limits.
+
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::context::JobContext;
+use crate::orchestrator::{ContainerJobManager, ScriptRun};
+use crate::tools::tool::{
+    ApprovalRequirement, Tool, ToolError, ToolOutput, ToolRateLimitConfig, require_str,
+};
+use crate::workspace::Workspace;
+
+/// Default wall-clock limit for a run.
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+/// Upper bound callers may request.
+const MAX_TIMEOUT_SECS: u64 = 300;
+/// Memory limit for script containers.
+const SCRIPT_MEMORY_MB: u64 = 1024;
+/// Combined stdout/stderr kept from a run.
+const MAX_LOG_BYTES: usize = 64 * 1024;
+/// Largest script accepted.
+const MAX_CODE_BYTES: usize = 256 * 1024;
+/// Most workspace documents one run may mount.
+const MAX_INPUTS: usize = 50;
+/// Most output files copied back into the workspace.
+const MAX_OUTPUT_FILES: usize = 20;
+/// Largest single output file copied back into the workspace.
+const MAX_OUTPUT_FILE_BYTES: u64 = 1024 * 1024;
+/// Workspace directory for outputs when no active matter applies.
+const DEFAULT_OUTPUT_ROOT: &str = "run-code";
+
+/// Interpreters available in the worker image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    Python,
+    Node,
+    Shell,
+}
+
+impl Language {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "python" | "python3" | "py" => Some(Self::Python),
+            "node" | "javascript" | "js" => Some(Self::Node),
+            "shell" | "sh" | "bash" => Some(Self::Shell),
+            _ => None,
+        }
+    }
+
+    fn script_name(self) -> &'static str {
+        match self {
+            Self::Python => "main.py",
+            Self::Node => "main.js",
+            Self::Shell => "main.sh",
+        }
+    }
+
+    fn command(self) -> Vec<String> {
+        let interpreter = match self {
+            Self::Python => "python3",
+            Self::Node => "node",
+            Self::Shell => "bash",
+        };
+        vec![
+            interpreter.to_string(),
+            format!("/workspace/script/{}", self.script_name()),
+        ]
+    }
+}
+
+/// Normalize a workspace path and reject anything that could escape the
+/// staging directory once joined onto it.
+fn clean_workspace_path(raw: &str) -> Result<String, ToolError> {
+    let trimmed = raw.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        return Err(ToolError::InvalidParameters(
+            "workspace paths cannot be empty".to_string(),
+        ));
+    }
+    if !Path::new(trimmed)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+    {
+        return Err(ToolError::InvalidParameters(format!(
+            "path '{raw}' must be a plain relative workspace path"
+        )));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Host directory for one run's mounts. Lives under the projects base so
+/// the job manager's bind-mount validation accepts it.
+fn staging_root(run_id: Uuid) -> Result<PathBuf, ToolError> {
+    let home = dirs::home_dir().ok_or_else(|| {
+        ToolError::ExecutionFailed("could not determine home directory".to_string())
+    })?;
+    Ok(home
+        .join(".clawyer")
+        .join("projects")
+        .join(".run-code")
+        .join(run_id.to_string()))
+}
+
+/// An output file that was not copied back, and why.
+#[derive(Debug, PartialEq, Eq, serde::Serialize)]
+struct SkippedOutput {
+    file: String,
+    reason: String,
+}
+
+/// Copy text files the script left in `dir` into the workspace under
+/// `output_dir`, returning the workspace paths written.
+async fn collect_outputs(
+    workspace: &Workspace,
+    dir: &Path,
+    output_dir: &str,
+) -> Result<(Vec<String>, Vec<SkippedOutput>), ToolError> {
+    let mut written = Vec::new();
+    let mut skipped = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let mut entries = tokio::fs::read_dir(&current)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Reading outputs failed: {e}")))?;
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Reading outputs failed: {e}")))?
+        {
+            let path = entry.path();
+            let relative = path
+                .strip_prefix(dir)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            // Never follow links the script created; they could point at
+            // anything on the host.
+            let file_type = entry
+                .file_type()
+                .await
+                .map_err(|e| ToolError::ExecutionFailed(format!("Reading outputs failed: {e}")))?;
+            if file_type.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let reason = if !file_type.is_file() {
+                Some("not a regular file".to_string())
+            } else if written.len() >= MAX_OUTPUT_FILES {
+                Some(format!("more than {MAX_OUTPUT_FILES} output files"))
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                skipped.push(SkippedOutput {
+                    file: relative,
+                    reason,
+                });
+                continue;
+            }
+            let size = entry.metadata().await.map(|m| m.len()).unwrap_or(u64::MAX);
+            if size > MAX_OUTPUT_FILE_BYTES {
+                skipped.push(SkippedOutput {
+                    file: relative,
+                    reason: format!("larger than {MAX_OUTPUT_FILE_BYTES} bytes"),
+                });
+                continue;
+            }
+            let bytes = tokio::fs::read(&path)
+                .await
+                .map_err(|e| ToolError::ExecutionFailed(format!("Reading outputs failed: {e}")))?;
+            let Ok(content) = String::from_utf8(bytes) else {
+                skipped.push(SkippedOutput {
+                    file: relative,
+                    reason: "not UTF-8 text".to_string(),
+                });
+                continue;
+            };
+            let target = format!("{output_dir}/{relative}");
+            workspace
+                .write(&target, &content)
+                .await
+                .map_err(|e| ToolError::ExecutionFailed(format!("Write failed: {e}")))?;
+            written.push(target);
+        }
+    }
+    written.sort();
+    Ok((written, skipped))
+}
+
+/// Runs short Python/Node/shell scripts over workspace documents in a
+/// sandboxed container.
+pub struct RunCodeTool {
+    job_manager: Arc<ContainerJobManager>,
+    workspace: Arc<Workspace>,
+    legal: Option<crate::config::LegalConfig>,
+}
+
+impl RunCodeTool {
+    pub fn new(job_manager: Arc<ContainerJobManager>, workspace: Arc<Workspace>) -> Self {
+        Self {
+            job_manager,
+            workspace,
+            legal: None,
+        }
+    }
+
+    pub fn with_legal_policy(mut self, legal: crate::config::LegalConfig) -> Self {
+        self.legal = Some(legal);
+        self
+    }
+
+    /// Workspace prefix the run is confined to, when legal mode has an
+    /// active matter.
+    fn matter_scope(&self, ctx: &JobContext) -> Option<String> {
+        let legal = self.legal.as_ref().filter(|l| l.enabled)?;
+        let matter_id = super::memory::active_matter_for_ctx(legal, ctx)?;
+        Some(crate::legal::matter::matter_prefix(legal, &matter_id))
+    }
+
+    /// Validate input paths against the active matter so a script cannot be
+    /// pointed at another client's documents.
+    fn resolve_inputs(
+        &self,
+        params: &serde_json::Value,
+        scope: Option<&str>,
+    ) -> Result<Vec<String>, ToolError> {
+        let raw = match params.get("inputs") {
+            None | Some(serde_json::Value::Null) => return Ok(Vec::new()),
+            Some(serde_json::Value::Array(items)) => items,
+            Some(_) => {
+                return Err(ToolError::InvalidParameters(
+                    "inputs must be an array of workspace paths".to_string(),
+                ));
+            }
+        };
+        if raw.len() > MAX_INPUTS {
+            return Err(ToolError::InvalidParameters(format!(
+                "at most {MAX_INPUTS} inputs may be mounted per run"
+            )));
+        }
+        let mut inputs = Vec::with_capacity(raw.len());
+        for item in raw {
+            let path = item.as_str().ok_or_else(|| {
+                ToolError::InvalidParameters("inputs must be strings".to_string())
+            })?;
+            let path = clean_workspace_path(path)?;
+            if let Some(scope) = scope
+                && !path.starts_with(&format!("{scope}/"))
+            {
+                return Err(ToolError::NotAuthorized(format!(
+                    "input '{path}' is outside the active matter scope '{scope}'"
+                )));
+            }
+            if !inputs.contains(&path) {
+                inputs.push(path);
+            }
+        }
+        Ok(inputs)
+    }
+
+    fn resolve_output_dir(
+        &self,
+        params: &serde_json::Value,
+        scope: Option<&str>,
+        run_id: Uuid,
+    ) -> Result<String, ToolError> {
+        let requested = params
+            .get("output_dir")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.trim().is_empty());
+        let Some(requested) = requested else {
+            let base = scope
+                .map(|scope| format!("{scope}/analysis"))
+                .unwrap_or_else(|| DEFAULT_OUTPUT_ROOT.to_string());
+            return Ok(format!("{base}/{run_id}"));
+        };
+        let dir = clean_workspace_path(requested)?;
+        match scope {
+            Some(scope) if dir == scope || dir.starts_with(&format!("{scope}/")) => Ok(dir),
+            Some(scope) => Err(ToolError::NotAuthorized(format!(
+                "output_dir '{dir}' is outside the active matter scope '{scope}'"
+            ))),
+            None => Ok(dir),
+        }
+    }
+
+    async fn stage(
+        &self,
+        root: &Path,
+        language: Language,
+        code: &str,
+        inputs: &[String],
+    ) -> Result<(), ToolError> {
+        let io = |e: std::io::Error| ToolError::ExecutionFailed(format!("Staging failed: {e}"));
+        for sub in ["script", "inputs", "outputs"] {
+            tokio::fs::create_dir_all(root.join(sub))
+                .await
+                .map_err(io)?;
+        }
+        tokio::fs::write(root.join("script").join(language.script_name()), code)
+            .await
+            .map_err(io)?;
+        for path in inputs {
+            let doc =
+                self.workspace.read(path).await.map_err(|e| {
+                    ToolError::ExecutionFailed(format!("Read of '{path}' failed: {e}"))
+                })?;
+            let target = root.join("inputs").join(path);
+            if let Some(parent) = target.parent() {
+                tokio::fs::create_dir_all(parent).await.map_err(io)?;
+            }
+            tokio::fs::write(&target, doc.content).await.map_err(io)?;
+        }
+        // The container runs as uid 1000, which may not own these files.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            tokio::fs::set_permissions(
+                root.join("outputs"),
+                std::fs::Permissions::from_mode(0o777),
+            )
+            .await
+            .map_err(io)?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Tool for RunCodeTool {
+    fn name(&self) -> &str {
+        "run_code"
+    }
+
+    fn description(&self) -> &str {
+        "Run a short Python, Node, or shell script in an isolated container with no network. \
+         Listed workspace documents are mounted read-only under /workspace/inputs/<path>; files \
+         the script writes to /workspace/outputs/ are saved back into the workspace. Use for \
+         damages calculations, spreadsheet/CSV wrangling, and discovery data clean-up."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "language": {
+                    "type": "string",
+                    "enum": ["python", "node", "shell"],
+                    "description": "Interpreter for the script"
+                },
+                "code": {
+                    "type": "string",
+                    "description": "Script source. Read inputs from /workspace/inputs/ and write results to /workspace/outputs/"
+                },
+                "inputs": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Workspace document paths to mount read-only, e.g. 'matters/acme-v-foo/discovery/invoices.csv'"
+                },
+                "output_dir": {
+                    "type": "string",
+                    "description": "Workspace directory to save output files into (default: the active matter's analysis/<run id>/ folder)"
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": format!("Time limit in seconds (default {DEFAULT_TIMEOUT_SECS}, max {MAX_TIMEOUT_SECS})")
+                }
+            },
+            "required": ["language", "code"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: &JobContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let start = Instant::now();
+        let language_raw = require_str(&params, "language")?;
+        let language = Language::parse(language_raw).ok_or_else(|| {
+            ToolError::InvalidParameters(format!(
+                "unsupported language '{language_raw}'; use python, node, or shell"
+            ))
+        })?;
+        let code = require_str(&params, "code")?;
+        if code.trim().is_empty() {
+            return Err(ToolError::InvalidParameters(
+                "code cannot be empty".to_string(),
+            ));
+        }
+        if code.len() > MAX_CODE_BYTES {
+            return Err(ToolError::InvalidParameters(format!(
+                "code exceeds {MAX_CODE_BYTES} bytes"
+            )));
+        }
+        let timeout = Duration::from_secs(
+            params
+                .get("timeout_secs")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(DEFAULT_TIMEOUT_SECS)
+                .clamp(1, MAX_TIMEOUT_SECS),
+        );
+
+        let run_id = Uuid::new_v4();
+        let scope = self.matter_scope(ctx);
+        let inputs = self.resolve_inputs(&params, scope.as_deref())?;
+        let output_dir = self.resolve_output_dir(&params, scope.as_deref(), run_id)?;
+
+        let root = staging_root(run_id)?;
+        let result = async {
+            self.stage(&root, language, code, &inputs).await?;
+            let output = self
+                .job_manager
+                .run_script(
+                    run_id,
+                    ScriptRun {
+                        command: language.command(),
+                        script_dir: root.join("script"),
+                        input_dir: root.join("inputs"),
+                        output_dir: root.join("outputs"),
+                        memory_limit_mb: SCRIPT_MEMORY_MB,
+                        timeout,
+                        max_output_bytes: MAX_LOG_BYTES,
+                    },
+                )
+                .await
+                .map_err(|e| match e {
+                    crate::error::OrchestratorError::ScriptTimeout { timeout, .. } => {
+                        ToolError::Timeout(timeout)
+                    }
+                    other => ToolError::ExecutionFailed(format!("Script run failed: {other}")),
+                })?;
+            let (written, skipped) =
+                collect_outputs(&self.workspace, &root.join("outputs"), &output_dir).await?;
+            Ok::<_, ToolError>((output, written, skipped))
+        }
+        .await;
+        if let Err(e) = tokio::fs::remove_dir_all(&root).await {
+            tracing::warn!(run_id = %run_id, error = %e, "Failed to remove run_code staging dir");
+        }
+        let (output, written, skipped) = result?;
+
+        Ok(ToolOutput::success(
+            serde_json::json!({
+                "run_id": run_id.to_string(),
+                "language": language_raw,
+                "exit_code": output.exit_code,
+                "success": output.exit_code == 0,
+                "stdout": output.stdout,
+                "stderr": output.stderr,
+                "truncated": output.truncated,
+                "inputs": inputs,
+                "output_dir": output_dir,
+                "outputs": written,
+                "skipped_outputs": skipped,
+                "duration_ms": output.duration.as_millis() as u64,
+            }),
+            start.elapsed(),
+        ))
+    }
+
+    fn requires_approval(&self, _params: &serde_json::Value) -> ApprovalRequirement {
+        ApprovalRequirement::UnlessAutoApproved
+    }
+
+    fn execution_timeout(&self) -> Duration {
+        // Container start and output collection on top of the script limit.
+        Duration::from_secs(MAX_TIMEOUT_SECS + 60)
+    }
+
+    fn requires_sanitization(&self) -> bool {
+        true // Script output can echo untrusted document content
+    }
+
+    fn rate_limit_config(&self) -> Option<ToolRateLimitConfig> {
+        Some(ToolRateLimitConfig::new(10, 100))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestrator::{ContainerJobConfig, TokenStore};
+
+    fn tool(workspace: Arc<Workspace>) -> RunCodeTool {
+        let jm = Arc::new(ContainerJobManager::new(
+            ContainerJobConfig::default(),
+            TokenStore::new(),
+        ));
+        RunCodeTool::new(jm, workspace)
+    }
+
+    fn legal_ctx(matter: &str) -> (crate::config::LegalConfig, JobContext) {
+        let mut legal = crate::config::LegalConfig::resolve(&crate::settings::Settings::default())
+            .expect("default legal config resolves");
+        legal.enabled = true;
+        legal.active_matter = Some(matter.to_string());
+        (legal, JobContext::default())
+    }
+
+    #[test]
+    fn language_aliases_map_to_interpreters() {
+        assert_eq!(Language::parse("Python3"), Some(Language::Python));
+        assert_eq!(Language::parse("js"), Some(Language::Node));
+        assert_eq!(Language::parse("bash"), Some(Language::Shell));
+        assert_eq!(Language::parse("ruby"), None);
+        assert_eq!(
+            Language::Python.command(),
+            vec!["python3", "/workspace/script/main.py"]
+        );
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn inputs_and_outputs_stay_inside_active_matter() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", db));
+        let (legal, ctx) = legal_ctx("acme");
+        let tool = tool(workspace).with_legal_policy(legal);
+        let scope = tool.matter_scope(&ctx);
+        assert_eq!(scope.as_deref(), Some("matters/acme"));
+
+        let ok = serde_json::json!({"inputs": ["matters/acme/discovery/a.csv", "/matters/acme/discovery/a.csv"]});
+        assert_eq!(
+            tool.resolve_inputs(&ok, scope.as_deref()).unwrap(),
+            vec!["matters/acme/discovery/a.csv"]
+        );
+        for bad in ["matters/other/secret.csv", "matters/acme/../other/x.csv"] {
+            let params = serde_json::json!({ "inputs": [bad] });
+            assert!(
+                tool.resolve_inputs(&params, scope.as_deref()).is_err(),
+                "{bad}"
+            );
+        }
+
+        let run_id = Uuid::nil();
+        assert_eq!(
+            tool.resolve_output_dir(&serde_json::json!({}), scope.as_deref(), run_id)
+                .unwrap(),
+            format!("matters/acme/analysis/{run_id}")
+        );
+        assert!(matches!(
+            tool.resolve_output_dir(
+                &serde_json::json!({"output_dir": "matters/other/out"}),
+                scope.as_deref(),
+                run_id
+            ),
+            Err(ToolError::NotAuthorized(_))
+        ));
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn collects_text_outputs_into_workspace() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Workspace::new_with_db("default", db);
+        let out = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(out.path().join("tables")).unwrap();
+        std::fs::write(out.path().join("summary.md"), "Total: $1,200").unwrap();
+        std::fs::write(out.path().join("tables/damages.csv"), "item,amount\n").unwrap();
+        std::fs::write(out.path().join("chart.png"), [0x89u8, 0x50, 0xff, 0xfe]).unwrap();
+
+        let (written, skipped) = collect_outputs(&workspace, out.path(), "matters/acme/analysis")
+            .await
+            .unwrap();
+        assert_eq!(
+            written,
+            vec![
+                "matters/acme/analysis/summary.md",
+                "matters/acme/analysis/tables/damages.csv"
+            ]
+        );
+        assert_eq!(
+            skipped,
+            vec![SkippedOutput {
+                file: "chart.png".to_string(),
+                reason: "not UTF-8 text".to_string()
+            }]
+        );
+        let doc = workspace
+            .read("matters/acme/analysis/summary.md")
+            .await
+            .unwrap();
+        assert_eq!(doc.content, "Total: $1,200");
+    }
+}
@@ -0,0 +1,554 @@
+//! Client status report drafting tool.
+//!
+//! Drafts are queued for attorney approval; this tool never sends email.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::context::JobContext;
+use crate::db::{
+    ClientRecord, ClientStatusReportStatus, Database, DocumentReadinessState,
+    MatterDocumentCategory, MatterRecord, MatterStatus, UpsertClientStatusReportParams,
+    UpsertMatterDocumentParams,
+};
+use crate::legal::locale::{Locale, client_locale, drafting_instruction, template_variant_name};
+use crate::legal::status_report::{
+    StatusReportFacts, TEMPLATE_BODY, TEMPLATE_NAME, collect_status_facts, render_status_report,
+    report_path, report_subject,
+};
+use crate::tools::tool::{Tool, ToolError, ToolOutput, ToolRateLimitConfig, db_err};
+use crate::workspace::Workspace;
+
+const DEFAULT_LOOKBACK_DAYS: i64 = 7;
+const DEFAULT_LOOKAHEAD_DAYS: i64 = 30;
+const MAX_NEXT_STEPS: usize = 10;
+
+/// Gathers matter facts and queues client status report drafts.
+pub struct ClientStatusReportTool {
+    workspace: Arc<Workspace>,
+    store: Arc<dyn Database>,
+    legal: Option<crate::config::LegalConfig>,
+}
+
+impl ClientStatusReportTool {
+    pub fn new(workspace: Arc<Workspace>, store: Arc<dyn Database>) -> Self {
+        Self {
+            workspace,
+            store,
+            legal: None,
+        }
+    }
+
+    pub fn with_legal_policy(mut self, legal: crate::config::LegalConfig) -> Self {
+        self.legal = Some(legal);
+        self
+    }
+
+    async fn matter_facts(
+        &self,
+        user_id: &str,
+        matter: &MatterRecord,
+        lookback_days: i64,
+        lookahead_days: i64,
+    ) -> Result<(Option<ClientRecord>, StatusReportFacts), ToolError> {
+        let client = self
+            .store
+            .get_client(user_id, matter.client_id)
+            .await
+            .map_err(db_err)?;
+        let deadlines = self
+            .store
+            .list_matter_deadlines(user_id, &matter.matter_id)
+            .await
+            .map_err(db_err)?;
+        let tasks = self
+            .store
+            .list_matter_tasks(user_id, &matter.matter_id)
+            .await
+            .map_err(db_err)?;
+        let documents = self
+            .store
+            .list_matter_documents_db(user_id, &matter.matter_id)
+            .await
+            .map_err(db_err)?;
+        let facts = collect_status_facts(
+            Utc::now(),
+            lookback_days,
+            lookahead_days,
+            &deadlines,
+            &tasks,
+            &documents,
+        );
+        Ok((client, facts))
+    }
+
+    /// The client's preferred language, if they set one.
+    async fn client_language(
+        &self,
+        user_id: &str,
+        client: &ClientRecord,
+    ) -> Result<Option<String>, ToolError> {
+        Ok(self
+            .store
+            .get_client_communication_preferences(user_id, client.id)
+            .await
+            .map_err(db_err)?
+            .and_then(|prefs| prefs.language))
+    }
+
+    /// The client status template for `locale`: matter templates before
+    /// firm ones, a language variant before the base template, and
+    /// [`TEMPLATE_BODY`] last.
+    async fn status_template(
+        &self,
+        user_id: &str,
+        matter_id: &str,
+        locale: Locale,
+    ) -> Result<String, ToolError> {
+        let variant = template_variant_name(TEMPLATE_NAME, locale);
+        let mut names = vec![variant.as_str()];
+        if variant != TEMPLATE_NAME {
+            names.push(TEMPLATE_NAME);
+        }
+        for name in names {
+            if let Some(template) = self
+                .store
+                .get_document_template_by_name(user_id, Some(matter_id), name)
+                .await
+                .map_err(db_err)?
+            {
+                return Ok(template.body);
+            }
+        }
+        Ok(TEMPLATE_BODY.to_string())
+    }
+
+    async fn list_active(
+        &self,
+        ctx: &JobContext,
+        lookback_days: i64,
+        lookahead_days: i64,
+    ) -> Result<serde_json::Value, ToolError> {
+        let matters = self
+            .store
+            .list_matters_db(&ctx.user_id)
+            .await
+            .map_err(db_err)?;
+        let today = Utc::now().date_naive();
+        let mut out = Vec::new();
+        for matter in matters.iter().filter(|m| m.status == MatterStatus::Active) {
+            let (client, facts) = self
+                .matter_facts(&ctx.user_id, matter, lookback_days, lookahead_days)
+                .await?;
+            let language = match client.as_ref() {
+                Some(client) => self.client_language(&ctx.user_id, client).await?,
+                None => None,
+            };
+            out.push(serde_json::json!({
+                "matter_id": matter.matter_id,
+                "client_name": client.as_ref().map(|c| c.name.clone()),
+                "client_email_on_file": client.as_ref().is_some_and(|c| c.email.is_some()),
+                "client_language": language,
+                "drafting_instruction": language
+                    .as_deref()
+                    .and_then(|language| drafting_instruction(language, today)),
+                "facts": facts,
+            }));
+        }
+        Ok(serde_json::json!({ "matters": out }))
+    }
+
+    async fn draft(
+        &self,
+        ctx: &JobContext,
+        matter_id: &str,
+        summary: Option<&str>,
+        next_steps: &[String],
+        lookback_days: i64,
+        lookahead_days: i64,
+    ) -> Result<serde_json::Value, ToolError> {
+        let matter = self
+            .store
+            .get_matter_db(&ctx.user_id, matter_id)
+            .await
+            .map_err(db_err)?
+            .ok_or_else(|| {
+                ToolError::InvalidParameters(format!("matter '{matter_id}' not found"))
+            })?;
+        let (client, facts) = self
+            .matter_facts(&ctx.user_id, &matter, lookback_days, lookahead_days)
+            .await?;
+        let client = client.ok_or_else(|| {
+            ToolError::ExecutionFailed(format!("matter '{matter_id}' has no client record"))
+        })?;
+
+        let locale = client_locale(
+            self.client_language(&ctx.user_id, &client)
+                .await?
+                .as_deref(),
+        );
+        let template_body = self
+            .status_template(&ctx.user_id, matter_id, locale)
+            .await?;
+        let content = render_status_report(
+            &template_body,
+            locale,
+            &matter,
+            &client,
+            &facts,
+            summary,
+            next_steps,
+        )
+        .map_err(ToolError::ExecutionFailed)?;
+
+        let today = Utc::now().date_naive();
+        let path = report_path(
+            crate::legal::policy::matter_root(self.legal.as_ref()),
+            matter_id,
+            today,
+        );
+        let existing = self
+            .store
+            .list_client_status_reports(&ctx.user_id, matter_id)
+            .await
+            .map_err(db_err)?;
+        if existing
+            .iter()
+            .any(|r| r.report_path == path && r.status == ClientStatusReportStatus::Sent)
+        {
+            return Err(ToolError::ExecutionFailed(format!(
+                "today's status report for '{matter_id}' was already sent"
+            )));
+        }
+
+        let written = self
+            .workspace
+            .write(&path, &content)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Write failed: {e}")))?;
+        self.store
+            .upsert_matter_document(
+                &ctx.user_id,
+                matter_id,
+                &UpsertMatterDocumentParams {
+                    memory_document_id: written.id,
+                    path: written.path.clone(),
+                    display_name: format!("Client status update {today}"),
+                    category: MatterDocumentCategory::Correspondence,
+                    readiness_state: Some(DocumentReadinessState::Draft),
+                },
+            )
+            .await
+            .map_err(db_err)?;
+        let report = self
+            .store
+            .upsert_client_status_report(
+                &ctx.user_id,
+                matter_id,
+                &UpsertClientStatusReportParams {
+                    report_path: path,
+                    subject: report_subject(matter_id, today),
+                    recipient: client.email.clone(),
+                    created_by: ctx.user_id.clone(),
+                },
+            )
+            .await
+            .map_err(db_err)?;
+
+        Ok(serde_json::json!({
+            "report_id": report.id.to_string(),
+            "matter_id": report.matter_id,
+            "report_path": report.report_path,
+            "subject": report.subject,
+            "has_recipient": report.recipient.is_some(),
+            "status": report.status.as_str(),
+            "locale": locale.code(),
+            "note": "Queued for attorney approval. It will only be emailed after an attorney approves it.",
+        }))
+    }
+}
+
+fn days_param(
+    params: &serde_json::Value,
+    name: &str,
+    default: i64,
+    max: i64,
+) -> Result<i64, ToolError> {
+    match params.get(name).and_then(|v| v.as_i64()) {
+        None => Ok(default),
+        Some(days) if (1..=max).contains(&days) => Ok(days),
+        Some(_) => Err(ToolError::InvalidParameters(format!(
+            "{name} must be between 1 and {max}"
+        ))),
+    }
+}
+
+#[async_trait]
+impl Tool for ClientStatusReportTool {
+    fn name(&self) -> &str {
+        "client_status_report"
+    }
+
+    fn description(&self) -> &str {
+        "Draft a client-facing status update for a matter. Without 'matter_id', lists active \
+         matters with their recent activity, upcoming dates, and open tasks. With 'matter_id', \
+         renders the client status template and queues the draft for attorney approval; \
+         approved drafts are emailed to the client by the gateway. This tool never sends email."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "matter_id": {
+                    "type": "string",
+                    "description": "Matter to draft a report for. Omit to list active matters."
+                },
+                "summary": {
+                    "type": "string",
+                    "description": "Two to four plain-language sentences summarizing the period for the client"
+                },
+                "next_steps": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Concrete next steps to share with the client"
+                },
+                "lookback_days": {
+                    "type": "integer",
+                    "description": "Days of activity to report (default 7)"
+                },
+                "lookahead_days": {
+                    "type": "integer",
+                    "description": "Days of upcoming dates to include (default 30)"
+                }
+            }
+        })
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: &JobContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let start = Instant::now();
+        let lookback_days = days_param(&params, "lookback_days", DEFAULT_LOOKBACK_DAYS, 31)?;
+        let lookahead_days = days_param(&params, "lookahead_days", DEFAULT_LOOKAHEAD_DAYS, 180)?;
+
+        let Some(raw_matter_id) = params.get("matter_id").and_then(|v| v.as_str()) else {
+            let output = self.list_active(ctx, lookback_days, lookahead_days).await?;
+            return Ok(ToolOutput::success(output, start.elapsed()));
+        };
+        let matter_id = crate::legal::policy::sanitize_optional_matter_id(raw_matter_id)
+            .ok_or_else(|| ToolError::InvalidParameters("matter_id is empty".to_string()))?;
+        if let Some(scoped) = crate::legal::policy::matter_id_from_metadata(&ctx.metadata)
+            && scoped != matter_id
+        {
+            return Err(ToolError::NotAuthorized(format!(
+                "this job is scoped to matter '{scoped}'"
+            )));
+        }
+
+        let summary = params
+            .get("summary")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty());
+        let next_steps: Vec<String> = params
+            .get("next_steps")
+            .and_then(|v| v.as_array())
+            .map(|steps| {
+                steps
+                    .iter()
+                    .filter_map(|step| step.as_str())
+                    .map(str::trim)
+                    .filter(|step| !step.is_empty())
+                    .take(MAX_NEXT_STEPS)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let output = self
+            .draft(
+                ctx,
+                &matter_id,
+                summary,
+                &next_steps,
+                lookback_days,
+                lookahead_days,
+            )
+            .await?;
+        Ok(ToolOutput::success(output, start.elapsed()))
+    }
+
+    fn execution_timeout(&self) -> Duration {
+        Duration::from_secs(120)
+    }
+
+    fn requires_sanitization(&self) -> bool {
+        false
+    }
+
+    fn rate_limit_config(&self) -> Option<ToolRateLimitConfig> {
+        Some(ToolRateLimitConfig::new(20, 200))
+    }
+}
+
+#[cfg(all(test, feature = "libsql"))]
+mod tests {
+    use super::*;
+    use crate::db::{
+        ClientType, CommunicationChannel, CreateClientParams, CreateMatterDeadlineParams,
+        MatterDeadlineType, UpsertClientCommunicationPreferencesParams,
+        UpsertDocumentTemplateParams, UpsertMatterParams,
+    };
+
+    #[tokio::test]
+    async fn drafts_are_queued_for_approval() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", Arc::clone(&db)));
+        let ctx = JobContext::default();
+        let client = db
+            .create_client(
+                &ctx.user_id,
+                &CreateClientParams {
+                    name: "Acme Corp".to_string(),
+                    client_type: ClientType::Entity,
+                    email: Some("gc@acme.test".to_string()),
+                    phone: None,
+                    address: None,
+                    notes: None,
+                },
+            )
+            .await
+            .expect("client");
+        db.upsert_matter(
+            &ctx.user_id,
+            &UpsertMatterParams {
+                matter_id: "demo".to_string(),
+                client_id: client.id,
+                status: MatterStatus::Active,
+                stage: None,
+                practice_area: None,
+                jurisdiction: None,
+                opened_at: None,
+                closed_at: None,
+                assigned_to: Vec::new(),
+                custom_fields: serde_json::json!({}),
+            },
+        )
+        .await
+        .expect("matter");
+        db.create_matter_deadline(
+            &ctx.user_id,
+            "demo",
+            &CreateMatterDeadlineParams {
+                title: "Case management conference".to_string(),
+                deadline_type: MatterDeadlineType::CourtDate,
+                due_at: Utc::now() + chrono::Duration::days(5),
+                completed_at: None,
+                reminder_days: Vec::new(),
+                rule_ref: None,
+                computed_from: None,
+                task_id: None,
+                explanation: None,
+                rule_version: None,
+                is_unsupported: false,
+            },
+        )
+        .await
+        .expect("deadline");
+        let tool = ClientStatusReportTool::new(Arc::clone(&workspace), Arc::clone(&db));
+
+        let listed = tool
+            .execute(serde_json::json!({}), &ctx)
+            .await
+            .expect("list active matters");
+        let matters = listed.result["matters"].as_array().expect("matters");
+        assert_eq!(matters.len(), 1);
+        assert_eq!(matters[0]["client_email_on_file"], true);
+        assert_eq!(
+            matters[0]["facts"]["upcoming_dates"][0]["title"],
+            "Case management conference"
+        );
+
+        let drafted = tool
+            .execute(
+                serde_json::json!({
+                    "matter_id": "demo",
+                    "summary": "Discovery is on schedule.",
+                    "next_steps": ["Prepare for the conference"]
+                }),
+                &ctx,
+            )
+            .await
+            .expect("draft report");
+        assert_eq!(drafted.result["status"], "pending_approval");
+        let path = drafted.result["report_path"].as_str().expect("path");
+        let stored = workspace.read(path).await.expect("read draft");
+        assert!(stored.content.contains("Discovery is on schedule."));
+        assert!(stored.content.contains("Case management conference"));
+
+        let reports = db
+            .list_client_status_reports(&ctx.user_id, "demo")
+            .await
+            .expect("list reports");
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].recipient.as_deref(), Some("gc@acme.test"));
+
+        db.upsert_client_communication_preferences(
+            &ctx.user_id,
+            client.id,
+            &UpsertClientCommunicationPreferencesParams {
+                allowed_channels: vec![CommunicationChannel::Email],
+                quiet_hours: Vec::new(),
+                timezone: None,
+                language: Some("Spanish".to_string()),
+                disclaimers: Vec::new(),
+                updated_by: ctx.user_id.clone(),
+            },
+        )
+        .await
+        .expect("preferences");
+        db.upsert_document_template(
+            &ctx.user_id,
+            &UpsertDocumentTemplateParams {
+                matter_id: None,
+                name: "client_status_update.es.md".to_string(),
+                body: "# Informe para {{ client.name }}\n\n{{ extra.summary }}".to_string(),
+                variables_json: serde_json::json!([]),
+            },
+        )
+        .await
+        .expect("spanish template");
+        let listed = tool
+            .execute(serde_json::json!({}), &ctx)
+            .await
+            .expect("list active matters");
+        assert_eq!(listed.result["matters"][0]["client_language"], "Spanish");
+        assert!(
+            listed.result["matters"][0]["drafting_instruction"]
+                .as_str()
+                .is_some_and(|text| text.starts_with("Write the message in Spanish (español)."))
+        );
+        let drafted = tool
+            .execute(
+                serde_json::json!({
+                    "matter_id": "demo",
+                    "summary": "El descubrimiento va según lo previsto.",
+                }),
+                &ctx,
+            )
+            .await
+            .expect("draft spanish report");
+        assert_eq!(drafted.result["locale"], "es");
+        let path = drafted.result["report_path"].as_str().expect("path");
+        assert_eq!(
+            workspace.read(path).await.expect("read draft").content,
+            "# Informe para Acme Corp\n\nEl descubrimiento va según lo previsto."
+        );
+    }
+}
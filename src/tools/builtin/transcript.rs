@@ -0,0 +1,292 @@
+//! Testimony quoting tool for ingested deposition transcripts.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+
+use crate::context::JobContext;
+use crate::legal::transcript::{LineRef, format_citation, parse_transcript};
+use crate::tools::tool::{Tool, ToolError, ToolOutput, require_str};
+use crate::workspace::Workspace;
+
+/// Most search hits returned for a `query` lookup.
+const MAX_SEARCH_MATCHES: usize = 25;
+
+/// Quotes transcript lines with `Smith Dep. 45:12-18` citations.
+pub struct QuoteTestimonyTool {
+    workspace: Arc<Workspace>,
+    legal: Option<crate::config::LegalConfig>,
+}
+
+impl QuoteTestimonyTool {
+    pub fn new(workspace: Arc<Workspace>) -> Self {
+        Self {
+            workspace,
+            legal: None,
+        }
+    }
+
+    pub fn with_legal_policy(mut self, legal: crate::config::LegalConfig) -> Self {
+        self.legal = Some(legal);
+        self
+    }
+}
+
+fn optional_line_ref(params: &serde_json::Value, name: &str) -> Result<Option<LineRef>, ToolError> {
+    match params.get(name).and_then(|v| v.as_str()) {
+        None => Ok(None),
+        Some(raw) if raw.trim().is_empty() => Ok(None),
+        Some(raw) => LineRef::parse(raw).map(Some).ok_or_else(|| {
+            ToolError::InvalidParameters(format!(
+                "{name} must be a page:line reference like '45:12', got '{raw}'"
+            ))
+        }),
+    }
+}
+
+#[async_trait]
+impl Tool for QuoteTestimonyTool {
+    fn name(&self) -> &str {
+        "quote_testimony"
+    }
+
+    fn description(&self) -> &str {
+        "Quote deposition testimony from an ingested transcript with a page:line citation \
+         (e.g. 'Smith Dep. 45:12-18'). Pass 'from' (and optionally 'to') to quote a range, or \
+         'query' to find the lines where a phrase appears. Use the returned citation verbatim \
+         in drafts."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Workspace path of the transcript, e.g. 'matters/acme-v-foo/transcripts/smith-depo.md'"
+                },
+                "from": {
+                    "type": "string",
+                    "description": "First line to quote as page:line, e.g. '45:12'"
+                },
+                "to": {
+                    "type": "string",
+                    "description": "Last line to quote as page:line, e.g. '45:18' (defaults to 'from')"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Phrase to look up when no 'from' is given"
+                },
+                "witness": {
+                    "type": "string",
+                    "description": "Deponent name for the citation when the transcript does not name one"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: &JobContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let start = Instant::now();
+        let path = require_str(&params, "path")?.trim().trim_matches('/');
+        let from = optional_line_ref(&params, "from")?;
+        let to = optional_line_ref(&params, "to")?;
+        let query = params
+            .get("query")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|q| !q.is_empty());
+        if from.is_none() && query.is_none() {
+            return Err(ToolError::InvalidParameters(
+                "pass 'from' to quote a range or 'query' to search the transcript".to_string(),
+            ));
+        }
+
+        if path.split('/').any(|part| part == "..") {
+            return Err(ToolError::InvalidParameters(
+                "path must not contain '..' segments".to_string(),
+            ));
+        }
+        let matter_root = crate::legal::policy::matter_root(self.legal.as_ref());
+        let matter_id = crate::legal::workspace_crypto::matter_id_for_path(path, matter_root)
+            .ok_or_else(|| {
+                ToolError::InvalidParameters(format!(
+                    "path must be inside a matter folder under '{}/'",
+                    matter_root
+                ))
+            })?;
+        if let Some(legal) = self.legal.as_ref().filter(|l| l.enabled)
+            && let Some(active) = super::memory::active_matter_for_ctx(legal, ctx)
+            && active != matter_id
+        {
+            return Err(ToolError::NotAuthorized(format!(
+                "transcript belongs to matter '{matter_id}' but the active matter is '{active}'"
+            )));
+        }
+
+        let doc = self
+            .workspace
+            .read(path)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Read failed: {e}")))?;
+        let transcript = parse_transcript(&doc.content).map_err(|e| {
+            ToolError::ExecutionFailed(format!("'{path}' is not a transcript: {e}"))
+        })?;
+        let witness = params
+            .get("witness")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|w| !w.is_empty())
+            .map(str::to_string)
+            .or_else(|| transcript.witness.clone())
+            .ok_or_else(|| {
+                ToolError::InvalidParameters(
+                    "transcript does not name the deponent; pass 'witness'".to_string(),
+                )
+            })?;
+
+        let output = match from {
+            Some(from) => {
+                let to = to.unwrap_or(from);
+                let excerpt = transcript
+                    .excerpt(from, to)
+                    .map_err(ToolError::InvalidParameters)?;
+                let lines: Vec<serde_json::Value> = excerpt
+                    .iter()
+                    .map(|(at, text)| serde_json::json!({"line": at.to_string(), "text": text}))
+                    .collect();
+                let quote = excerpt
+                    .iter()
+                    .map(|(_, text)| text.trim())
+                    .filter(|text| !text.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                serde_json::json!({
+                    "matter_id": matter_id,
+                    "path": path,
+                    "witness": witness,
+                    "citation": format_citation(&witness, from, to),
+                    "quote": quote,
+                    "lines": lines,
+                })
+            }
+            None => {
+                let hits = transcript.search(query.unwrap_or_default());
+                let matches: Vec<serde_json::Value> = hits
+                    .iter()
+                    .take(MAX_SEARCH_MATCHES)
+                    .map(|(at, text)| {
+                        serde_json::json!({
+                            "citation": format_citation(&witness, *at, *at),
+                            "text": text,
+                        })
+                    })
+                    .collect();
+                serde_json::json!({
+                    "matter_id": matter_id,
+                    "path": path,
+                    "witness": witness,
+                    "query": query,
+                    "total_matches": hits.len(),
+                    "matches": matches,
+                })
+            }
+        };
+        Ok(ToolOutput::success(output, start.elapsed()))
+    }
+
+    fn requires_sanitization(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRANSCRIPT: &str = "\
+---
+kind: deposition_transcript
+witness: \"Jane Roe\"
+---
+
+# Deposition of Jane Roe
+
+## Page 45
+
+45:11 Q.   Where were you that night?
+45:12 A.   At the warehouse on Dock Street.
+45:13 Q.   Alone?
+45:14 A.   No, with the foreman.
+
+## Page 46
+
+46:1 Q.   Did the foreman lock the gate?
+46:2 A.   I never saw him lock it.
+";
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn quotes_ranges_and_searches_with_page_line_citations() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", Arc::clone(&db)));
+        workspace
+            .write("matters/acme/transcripts/roe-depo.md", TRANSCRIPT)
+            .await
+            .expect("write transcript");
+        let tool = QuoteTestimonyTool::new(workspace);
+
+        let output = tool
+            .execute(
+                serde_json::json!({
+                    "path": "matters/acme/transcripts/roe-depo.md",
+                    "from": "45:12",
+                    "to": "46:2"
+                }),
+                &JobContext::default(),
+            )
+            .await
+            .expect("quote");
+        let result = &output.result;
+        assert_eq!(result["citation"], "Roe Dep. 45:12-46:2");
+        assert_eq!(result["lines"].as_array().map(Vec::len), Some(5));
+        assert!(
+            result["quote"]
+                .as_str()
+                .unwrap()
+                .starts_with("A.   At the warehouse")
+        );
+
+        let output = tool
+            .execute(
+                serde_json::json!({
+                    "path": "matters/acme/transcripts/roe-depo.md",
+                    "query": "foreman"
+                }),
+                &JobContext::default(),
+            )
+            .await
+            .expect("search");
+        let matches = output.result["matches"].as_array().unwrap();
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0]["citation"], "Roe Dep. 45:14");
+        assert_eq!(matches[1]["citation"], "Roe Dep. 46:1");
+
+        let err = tool
+            .execute(
+                serde_json::json!({
+                    "path": "matters/acme/transcripts/roe-depo.md",
+                    "from": "47:1"
+                }),
+                &JobContext::default(),
+            )
+            .await
+            .expect_err("missing page");
+        assert!(matches!(err, ToolError::InvalidParameters(_)), "{err}");
+    }
+}
@@ -0,0 +1,297 @@
+//! Machine translation tool for foreign-language matter documents.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::context::JobContext;
+use crate::db::{Database, UpsertDocumentTranslationParams};
+use crate::legal::translation::{
+    is_machine_translation_path, machine_translation_path, normalize_language_tag,
+    render_machine_translation, translate_text,
+};
+use crate::llm::LlmProvider;
+use crate::tools::tool::{Tool, ToolError, ToolOutput, ToolRateLimitConfig, require_str};
+use crate::workspace::Workspace;
+
+/// Translates a matter document and stores the draft beside the source.
+pub struct TranslateDocumentTool {
+    workspace: Arc<Workspace>,
+    llm: Arc<dyn LlmProvider>,
+    store: Option<Arc<dyn Database>>,
+    legal: Option<crate::config::LegalConfig>,
+}
+
+impl TranslateDocumentTool {
+    pub fn new(
+        workspace: Arc<Workspace>,
+        llm: Arc<dyn LlmProvider>,
+        store: Option<Arc<dyn Database>>,
+    ) -> Self {
+        Self {
+            workspace,
+            llm,
+            store,
+            legal: None,
+        }
+    }
+
+    pub fn with_legal_policy(mut self, legal: crate::config::LegalConfig) -> Self {
+        self.legal = Some(legal);
+        self
+    }
+}
+
+fn optional_language(params: &serde_json::Value, name: &str) -> Result<Option<String>, ToolError> {
+    match params.get(name).and_then(|v| v.as_str()) {
+        None => Ok(None),
+        Some(raw) if raw.trim().is_empty() => Ok(None),
+        Some(raw) => normalize_language_tag(raw).map(Some).ok_or_else(|| {
+            ToolError::InvalidParameters(format!(
+                "{name} must be a language tag like 'es' or 'pt-BR', got '{raw}'"
+            ))
+        }),
+    }
+}
+
+#[async_trait]
+impl Tool for TranslateDocumentTool {
+    fn name(&self) -> &str {
+        "translate_document"
+    }
+
+    fn description(&self) -> &str {
+        "Machine-translate a foreign-language matter document (e.g. an exhibit) and store the \
+         draft beside the source. The result is an uncertified draft tracked for replacement \
+         by a certified human translation; do not present it as certified."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Workspace path of the source document inside a matter folder, e.g. 'matters/acme-v-foo/exhibits/contrato.md'"
+                },
+                "target_language": {
+                    "type": "string",
+                    "description": "Language tag to translate into, e.g. 'en'"
+                },
+                "source_language": {
+                    "type": "string",
+                    "description": "Language tag of the source, if known, e.g. 'es'"
+                }
+            },
+            "required": ["path", "target_language"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        params: serde_json::Value,
+        ctx: &JobContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let start = Instant::now();
+        let source_path = require_str(&params, "path")?.trim().trim_matches('/');
+        let target_language = optional_language(&params, "target_language")?.ok_or_else(|| {
+            ToolError::InvalidParameters("missing 'target_language' parameter".to_string())
+        })?;
+        let source_language = optional_language(&params, "source_language")?;
+
+        if source_path.split('/').any(|part| part == "..") {
+            return Err(ToolError::InvalidParameters(
+                "path must not contain '..' segments".to_string(),
+            ));
+        }
+        if is_machine_translation_path(source_path) {
+            return Err(ToolError::InvalidParameters(
+                "path is already a machine translation; translate the original source".to_string(),
+            ));
+        }
+        let matter_root = crate::legal::policy::matter_root(self.legal.as_ref());
+        let matter_id =
+            crate::legal::workspace_crypto::matter_id_for_path(source_path, matter_root)
+                .ok_or_else(|| {
+                    ToolError::InvalidParameters(format!(
+                        "path must be inside a matter folder under '{}/'",
+                        matter_root
+                    ))
+                })?;
+        if let Some(legal) = self.legal.as_ref().filter(|l| l.enabled)
+            && let Some(active) = super::memory::active_matter_for_ctx(legal, ctx)
+            && active != matter_id
+        {
+            return Err(ToolError::NotAuthorized(format!(
+                "document belongs to matter '{matter_id}' but the active matter is '{active}'"
+            )));
+        }
+
+        let source = self
+            .workspace
+            .read(source_path)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Read failed: {e}")))?;
+        if source.content.trim().is_empty() {
+            return Err(ToolError::ExecutionFailed(format!(
+                "'{source_path}' has no text to translate"
+            )));
+        }
+
+        let translated = translate_text(
+            self.llm.as_ref(),
+            &source.content,
+            source_language.as_deref(),
+            &target_language,
+        )
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(format!("Translation failed: {e}")))?;
+        let model = self.llm.active_model_name();
+        let translation_path = machine_translation_path(source_path, &target_language);
+        self.workspace
+            .write(
+                &translation_path,
+                &render_machine_translation(
+                    source_path,
+                    source_language.as_deref(),
+                    &target_language,
+                    &model,
+                    &translated,
+                ),
+            )
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Write failed: {e}")))?;
+
+        let record = match self.store.as_ref() {
+            Some(store) => Some(
+                store
+                    .upsert_document_translation(
+                        &ctx.user_id,
+                        &matter_id,
+                        &UpsertDocumentTranslationParams {
+                            source_path: source_path.to_string(),
+                            source_language: source_language.clone(),
+                            target_language: target_language.clone(),
+                            translation_path: translation_path.clone(),
+                            provider: model.clone(),
+                            created_by: ctx.user_id.clone(),
+                        },
+                    )
+                    .await
+                    .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?,
+            ),
+            None => None,
+        };
+
+        let mut output = serde_json::json!({
+            "matter_id": matter_id,
+            "source_path": source_path,
+            "translation_path": translation_path,
+            "source_language": source_language,
+            "target_language": target_language,
+            "model": model,
+            "status": "machine_draft",
+        });
+        if let Some(record) = record {
+            output["translation_id"] = serde_json::json!(record.id.to_string());
+            output["status"] = serde_json::json!(record.status.as_str());
+            if record.certified_path.is_some() {
+                output["certified_path"] = serde_json::json!(record.certified_path);
+            }
+        }
+        Ok(ToolOutput::success(output, start.elapsed()))
+    }
+
+    fn execution_timeout(&self) -> Duration {
+        // Long exhibits are translated in several sequential LLM calls.
+        Duration::from_secs(600)
+    }
+
+    fn requires_sanitization(&self) -> bool {
+        false
+    }
+
+    fn rate_limit_config(&self) -> Option<ToolRateLimitConfig> {
+        Some(ToolRateLimitConfig::new(10, 100))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::StubLlm;
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn translates_matter_document_and_records_draft() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", Arc::clone(&db)));
+        workspace
+            .write("matters/acme/exhibits/contrato.md", "Las partes acuerdan.")
+            .await
+            .expect("write source");
+        let tool = TranslateDocumentTool::new(
+            Arc::clone(&workspace),
+            Arc::new(StubLlm::new("The parties agree.")),
+            Some(Arc::clone(&db)),
+        );
+
+        let output = tool
+            .execute(
+                serde_json::json!({
+                    "path": "matters/acme/exhibits/contrato.md",
+                    "target_language": "EN",
+                    "source_language": "es"
+                }),
+                &JobContext::default(),
+            )
+            .await
+            .expect("translate");
+        let result = &output.result;
+        assert_eq!(
+            result["translation_path"],
+            "matters/acme/exhibits/contrato.en.machine-translation.md"
+        );
+        assert_eq!(result["status"], "machine_draft");
+
+        let stored = workspace
+            .read("matters/acme/exhibits/contrato.en.machine-translation.md")
+            .await
+            .expect("read translation");
+        assert!(stored.content.contains("uncertified draft"));
+        assert!(stored.content.contains("The parties agree."));
+
+        let records = db
+            .list_document_translations(&JobContext::default().user_id, "acme")
+            .await
+            .expect("list");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].source_language.as_deref(), Some("es"));
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn rejects_paths_outside_matters_and_existing_translations() {
+        let (db, _dir) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", Arc::clone(&db)));
+        let tool = TranslateDocumentTool::new(workspace, Arc::new(StubLlm::default()), None);
+        for path in [
+            "notes/contrato.md",
+            "matters/acme/exhibits/contrato.en.machine-translation.md",
+            "matters/acme/../other/secret.md",
+        ] {
+            let err = tool
+                .execute(
+                    serde_json::json!({"path": path, "target_language": "en"}),
+                    &JobContext::default(),
+                )
+                .await
+                .expect_err(path);
+            assert!(
+                matches!(err, ToolError::InvalidParameters(_)),
+                "{path}: {err}"
+            );
+        }
+    }
+}
@@ -1,7 +1,8 @@
 //! MCP client for connecting to MCP servers.
 //!
 //! Supports both local (unauthenticated) and hosted (OAuth-authenticated) servers.
-//! Uses the Streamable HTTP transport with session management.
+//! Uses the Streamable HTTP transport with session management, or the stdio
+//! transport for servers launched as child processes.
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -18,6 +19,7 @@ use crate::tools::mcp::protocol::{
     CallToolResult, InitializeResult, ListToolsResult, McpRequest, McpResponse, McpTool,
 };
 use crate::tools::mcp::session::McpSessionManager;
+use crate::tools::mcp::stdio::StdioProcess;
 use crate::tools::tool::{ApprovalRequirement, Tool, ToolError, ToolOutput};
 
 /// MCP client for communicating with MCP servers.
@@ -52,6 +54,9 @@ pub struct McpClient {
 
     /// Server configuration (for token secret name lookup).
     server_config: Option<McpServerConfig>,
+
+    /// Supervised child process for stdio servers (shared across clones).
+    stdio: Option<Arc<StdioProcess>>,
 }
 
 impl McpClient {
@@ -75,6 +80,7 @@ impl McpClient {
             secrets: None,
             user_id: "default".to_string(),
             server_config: None,
+            stdio: None,
         }
     }
 
@@ -95,6 +101,7 @@ impl McpClient {
             secrets: None,
             user_id: "default".to_string(),
             server_config: None,
+            stdio: None,
         }
     }
 
@@ -120,9 +127,30 @@ impl McpClient {
             secrets: Some(secrets),
             user_id: user_id.into(),
             server_config: Some(config),
+            stdio: None,
         }
     }
 
+    /// Spawn a local stdio server and connect to it.
+    ///
+    /// The process is supervised: it is health-checked in the background and
+    /// restarted when it exits or stops answering.
+    pub async fn connect_stdio(config: McpServerConfig) -> Result<Self, ToolError> {
+        let process = StdioProcess::spawn(config.clone()).await?;
+        Ok(Self {
+            server_url: String::new(),
+            server_name: config.name.clone(),
+            http_client: reqwest::Client::new(),
+            next_id: AtomicU64::new(1),
+            tools_cache: RwLock::new(None),
+            session_manager: None,
+            secrets: None,
+            user_id: "default".to_string(),
+            server_config: Some(config),
+            stdio: Some(process),
+        })
+    }
+
     /// Get the server name.
     pub fn server_name(&self) -> &str {
         &self.server_name
@@ -133,6 +161,11 @@ impl McpClient {
         &self.server_url
     }
 
+    /// Whether this client talks to a spawned stdio server.
+    pub fn is_stdio(&self) -> bool {
+        self.stdio.is_some()
+    }
+
     /// Get the next request ID.
     fn next_request_id(&self) -> u64 {
         self.next_id.fetch_add(1, Ordering::SeqCst)
@@ -168,6 +201,10 @@ impl McpClient {
     /// Send a request to the MCP server with auth and session headers.
     /// Automatically attempts token refresh on 401 errors.
     async fn send_request(&self, request: McpRequest) -> Result<McpResponse, ToolError> {
+        if let Some(ref process) = self.stdio {
+            return process.request(request).await;
+        }
+
         // Try up to 2 times: first attempt, then retry after token refresh
         for attempt in 0..2 {
             // Request both JSON and SSE as per MCP spec
@@ -316,6 +353,11 @@ impl McpClient {
     ///
     /// This should be called once per session to establish capabilities.
     pub async fn initialize(&self) -> Result<InitializeResult, ToolError> {
+        // Stdio servers complete the handshake each time they are spawned.
+        if let Some(ref process) = self.stdio {
+            return Ok(process.initialize_result().await);
+        }
+
         // Check if already initialized
         if let Some(ref session_manager) = self.session_manager
             && session_manager.is_initialized(&self.server_name).await
@@ -460,6 +502,46 @@ impl McpClient {
         self.list_tools().await?;
         Ok(())
     }
+
+    /// Check that the server is answering. Stdio servers that have exited or
+    /// hung are restarted as part of the check.
+    pub async fn health_check(&self) -> Result<(), ToolError> {
+        if let Some(ref process) = self.stdio {
+            return process.health_check().await;
+        }
+        let response = self
+            .send_request(McpRequest::new(self.next_request_id(), "ping", None))
+            .await?;
+        match response.error {
+            Some(error) => Err(ToolError::ExternalService(format!(
+                "MCP ping failed: {} (code {})",
+                error.message, error.code
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Restart a stdio server and drop cached tool definitions. HTTP servers
+    /// have no process to restart, so only the cache is cleared.
+    pub async fn restart(&self) -> Result<(), ToolError> {
+        self.clear_cache().await;
+        match self.stdio {
+            Some(ref process) => process.restart().await,
+            None => Ok(()),
+        }
+    }
+
+    /// Number of times a stdio server has been restarted.
+    pub fn restart_count(&self) -> u32 {
+        self.stdio.as_ref().map_or(0, |p| p.restart_count())
+    }
+
+    /// Stop a stdio server's process. Does nothing for HTTP servers.
+    pub async fn shutdown(&self) {
+        if let Some(ref process) = self.stdio {
+            process.shutdown().await;
+        }
+    }
 }
 
 impl Clone for McpClient {
@@ -474,6 +556,7 @@ impl Clone for McpClient {
             secrets: self.secrets.clone(),
             user_id: self.user_id.clone(),
             server_config: self.server_config.clone(),
+            stdio: self.stdio.clone(),
         }
     }
 }
@@ -1,6 +1,7 @@
 //! MCP server configuration.
 //!
-//! Stores configuration for connecting to hosted MCP servers.
+//! Stores configuration for connecting to hosted MCP servers, and for local
+//! servers spawned as child processes that speak MCP over stdio.
 //! Configuration is persisted at ~/.clawyer/mcp-servers.json.
 
 use std::collections::HashMap;
@@ -17,9 +18,23 @@ pub struct McpServerConfig {
     /// Unique name for this server (e.g., "notion", "github").
     pub name: String,
 
-    /// Server URL (must be HTTPS for remote servers).
+    /// Server URL (must be HTTPS for remote servers). Empty for stdio servers.
+    #[serde(default)]
     pub url: String,
 
+    /// Command to spawn for a local stdio server (e.g. `npx`). When set, the
+    /// server is launched as a child process instead of reached over HTTP.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+
+    /// Arguments passed to `command`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+
+    /// Extra environment variables for the spawned process.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+
     /// OAuth configuration (if server requires authentication).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub oauth: Option<OAuthConfig>,
@@ -43,12 +58,39 @@ impl McpServerConfig {
         Self {
             name: name.into(),
             url: url.into(),
+            command: None,
+            args: Vec::new(),
+            env: HashMap::new(),
             oauth: None,
             enabled: true,
             description: None,
         }
     }
 
+    /// Create a configuration for a local server spawned over stdio.
+    pub fn new_stdio(
+        name: impl Into<String>,
+        command: impl Into<String>,
+        args: Vec<String>,
+    ) -> Self {
+        Self {
+            command: Some(command.into()),
+            args,
+            ..Self::new(name, "")
+        }
+    }
+
+    /// Set environment variables for a stdio server.
+    pub fn with_env(mut self, env: HashMap<String, String>) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// Whether this server is spawned locally and spoken to over stdio.
+    pub fn is_stdio(&self) -> bool {
+        self.command.is_some()
+    }
+
     /// Set OAuth configuration.
     pub fn with_oauth(mut self, oauth: OAuthConfig) -> Self {
         self.oauth = Some(oauth);
@@ -69,6 +111,25 @@ impl McpServerConfig {
             });
         }
 
+        if let Some(ref command) = self.command {
+            if command.trim().is_empty() {
+                return Err(ConfigError::InvalidConfig {
+                    reason: "Server command cannot be empty".to_string(),
+                });
+            }
+            if !self.url.is_empty() {
+                return Err(ConfigError::InvalidConfig {
+                    reason: "Set either a server URL or a command, not both".to_string(),
+                });
+            }
+            if self.oauth.is_some() {
+                return Err(ConfigError::InvalidConfig {
+                    reason: "OAuth is only supported for HTTP servers".to_string(),
+                });
+            }
+            return Ok(());
+        }
+
         if self.url.is_empty() {
             return Err(ConfigError::InvalidConfig {
                 reason: "Server URL cannot be empty".to_string(),
@@ -92,6 +153,9 @@ impl McpServerConfig {
     /// Returns true if OAuth is pre-configured OR if this is a remote HTTPS server
     /// (which likely supports Dynamic Client Registration even without pre-configured OAuth).
     pub fn requires_auth(&self) -> bool {
+        if self.is_stdio() {
+            return false;
+        }
         if self.oauth.is_some() {
             return true;
         }
@@ -468,6 +532,38 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_stdio_server_config() {
+        let config = McpServerConfig::new_stdio(
+            "filesystem",
+            "npx",
+            vec![
+                "-y".to_string(),
+                "@modelcontextprotocol/server-filesystem".to_string(),
+            ],
+        );
+        assert!(config.is_stdio());
+        assert!(config.validate().is_ok());
+        assert!(!config.requires_auth());
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["command"], "npx");
+        let parsed: McpServerConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.args.len(), 2);
+
+        let mut both = config.clone();
+        both.url = "https://mcp.example.com".to_string();
+        assert!(both.validate().is_err());
+
+        let blank = McpServerConfig::new_stdio("blank", " ", Vec::new());
+        assert!(blank.validate().is_err());
+
+        // Older config files have no command field and stay HTTP servers.
+        let legacy: McpServerConfig =
+            serde_json::from_str(r#"{"name":"notion","url":"https://mcp.notion.com"}"#).unwrap();
+        assert!(!legacy.is_stdio());
+    }
+
     #[test]
     fn test_oauth_config_builder() {
         let oauth = OAuthConfig::new("client-123")
@@ -3,7 +3,9 @@
 //! MCP allows the agent to connect to external tool servers that provide
 //! additional capabilities through a standardized protocol.
 //!
-//! Supports both local (unauthenticated) and hosted (OAuth-authenticated) servers.
+//! Supports both local (unauthenticated) and hosted (OAuth-authenticated) servers,
+//! plus local servers spawned as child processes over stdio.
+//! [`McpServer`] runs the other direction, exposing cLawyer itself to MCP clients.
 //!
 //! ## Usage
 //!
@@ -30,10 +32,13 @@ pub mod auth;
 mod client;
 pub mod config;
 mod protocol;
+pub mod server;
 pub mod session;
+mod stdio;
 
 pub use auth::{is_authenticated, refresh_access_token};
 pub use client::McpClient;
 pub use config::{McpServerConfig, McpServersFile, OAuthConfig};
 pub use protocol::{InitializeResult, McpRequest, McpResponse, McpTool};
+pub use server::McpServer;
 pub use session::McpSessionManager;
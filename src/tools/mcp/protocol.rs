@@ -37,15 +37,16 @@ fn default_input_schema() -> serde_json::Value {
 pub struct McpToolAnnotations {
     /// Hint that this tool performs destructive operations that cannot be undone.
     /// Tools with this hint set to true should require user approval before execution.
-    #[serde(default)]
+    /// The MCP spec spells it `destructiveHint`.
+    #[serde(default, alias = "destructiveHint")]
     pub destructive_hint: bool,
 
     /// Hint that this tool may have side effects beyond its return value.
-    #[serde(default)]
+    #[serde(default, alias = "sideEffectsHint")]
     pub side_effects_hint: bool,
 
     /// Hint that this tool performs read-only operations.
-    #[serde(default)]
+    #[serde(default, alias = "readOnlyHint")]
     pub read_only_hint: bool,
 
     /// Hint about the expected execution time category.
@@ -340,6 +341,18 @@ mod tests {
         assert!(props.get("query").is_some());
     }
 
+    #[test]
+    fn test_mcp_tool_spec_annotations_require_approval() {
+        // Servers send the spec's camelCase hint names.
+        let json = serde_json::json!({
+            "name": "delete_file",
+            "annotations": { "destructiveHint": true, "readOnlyHint": false }
+        });
+
+        let tool: McpTool = serde_json::from_value(json).expect("deserialize McpTool");
+        assert!(tool.requires_approval());
+    }
+
     #[test]
     fn test_mcp_tool_missing_schema_gets_default() {
         let json = serde_json::json!({
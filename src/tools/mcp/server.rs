@@ -0,0 +1,857 @@
+//! MCP server mode.
+//!
+//! `clawyer --mcp-serve` publishes the orchestrator tool registry, workspace
+//! documents, workspace search, and matter context to MCP clients such as
+//! Claude Desktop. Two transports are supported: newline-delimited JSON-RPC
+//! over stdio, and the HTTP+SSE transport (`GET /sse` for the event stream,
+//! `POST /messages?session_id=...` for requests), which requires a bearer
+//! token.
+//!
+//! Tool calls follow the same rules as autonomous jobs: tools that need
+//! approval are refused, parameters are validated, `BeforeToolCall` hooks
+//! and rate limits apply, and output is sanitized before it leaves the
+//! process. Every tool call and resource read is audited.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
+    routing::{get, post},
+};
+use futures::StreamExt;
+use serde::Deserialize;
+use subtle::ConstantTimeEq;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+use tokio::sync::{Mutex, mpsc};
+use uuid::Uuid;
+
+use super::protocol::PROTOCOL_VERSION;
+use crate::config::LegalConfig;
+use crate::context::JobContext;
+use crate::db::{AuditSeverity, Database};
+use crate::hooks::{HookError, HookEvent, HookOutcome, HookRegistry};
+use crate::safety::SafetyLayer;
+use crate::tools::rate_limiter::RateLimitResult;
+use crate::tools::{ToolDomain, ToolRegistry};
+use crate::workspace::Workspace;
+
+/// Matches at most this many chunks per `workspace-search://` read.
+const SEARCH_RESULT_LIMIT: usize = 10;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+/// Audit actor recorded for requests arriving over MCP.
+const MCP_ACTOR: &str = "mcp";
+
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(INVALID_PARAMS, message)
+    }
+}
+
+fn rpc_result(id: serde_json::Value, result: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn rpc_error(id: serde_json::Value, err: RpcError) -> serde_json::Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": err.code, "message": err.message },
+    })
+}
+
+/// MCP server over the agent's tools, workspace, and matters.
+pub struct McpServer {
+    tools: Arc<ToolRegistry>,
+    safety: Arc<SafetyLayer>,
+    hooks: Option<Arc<HookRegistry>>,
+    workspace: Option<Arc<Workspace>>,
+    store: Option<Arc<dyn Database>>,
+    legal: Option<LegalConfig>,
+    user_id: String,
+}
+
+impl McpServer {
+    pub fn new(tools: Arc<ToolRegistry>, safety: Arc<SafetyLayer>, user_id: &str) -> Self {
+        Self {
+            tools,
+            safety,
+            hooks: None,
+            workspace: None,
+            store: None,
+            legal: None,
+            user_id: user_id.to_string(),
+        }
+    }
+
+    pub fn with_hooks(mut self, hooks: Arc<HookRegistry>) -> Self {
+        self.hooks = Some(hooks);
+        self
+    }
+
+    pub fn with_workspace(mut self, workspace: Arc<Workspace>) -> Self {
+        self.workspace = Some(workspace);
+        self
+    }
+
+    pub fn with_store(mut self, store: Arc<dyn Database>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    pub fn with_legal_config(mut self, legal: LegalConfig) -> Self {
+        self.legal = Some(legal);
+        self
+    }
+
+    /// Handle one JSON-RPC message. Returns `None` for notifications.
+    pub async fn handle_message(&self, message: serde_json::Value) -> Option<serde_json::Value> {
+        let Some(object) = message.as_object() else {
+            return Some(rpc_error(
+                serde_json::Value::Null,
+                RpcError::new(INVALID_REQUEST, "Expected a JSON-RPC request object"),
+            ));
+        };
+        let Some(id) = object.get("id").cloned() else {
+            // Notifications (e.g. `notifications/initialized`) get no reply.
+            return None;
+        };
+        let Some(method) = object.get("method").and_then(|m| m.as_str()) else {
+            return Some(rpc_error(
+                id,
+                RpcError::new(INVALID_REQUEST, "Missing method"),
+            ));
+        };
+        let params = object
+            .get("params")
+            .cloned()
+            .unwrap_or(serde_json::Value::Null);
+        Some(match self.dispatch(method, params).await {
+            Ok(result) => rpc_result(id, result),
+            Err(err) => rpc_error(id, err),
+        })
+    }
+
+    async fn dispatch(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, RpcError> {
+        match method {
+            "initialize" => Ok(self.initialize()),
+            "ping" => Ok(serde_json::json!({})),
+            "tools/list" => Ok(self.list_tools().await),
+            "tools/call" => self.call_tool(params).await,
+            "resources/list" => self.list_resources().await,
+            "resources/templates/list" => Ok(resource_templates()),
+            "resources/read" => self.read_resource(params).await,
+            other => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Method '{other}' not supported"),
+            )),
+        }
+    }
+
+    fn initialize(&self) -> serde_json::Value {
+        serde_json::json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": {
+                "tools": { "listChanged": false },
+                "resources": { "subscribe": false, "listChanged": false },
+            },
+            "serverInfo": { "name": "clawyer", "version": env!("CARGO_PKG_VERSION") },
+            "instructions": "cLawyer legal workspace. Read matter://{matter_id} for matter \
+                context, workspace:///{path} for documents, and workspace-search:///{query} \
+                to search. Tools that need attorney approval are not callable over MCP.",
+        })
+    }
+
+    async fn list_tools(&self) -> serde_json::Value {
+        let mut tools: Vec<serde_json::Value> = self
+            .tools
+            .all()
+            .await
+            .into_iter()
+            .filter(|tool| tool.domain() == ToolDomain::Orchestrator)
+            .map(|tool| {
+                let needs_approval = self
+                    .tools
+                    .approval_decision_for(
+                        tool.name(),
+                        tool.requires_approval(&serde_json::json!({})),
+                        false,
+                    )
+                    .needs_approval;
+                let mut entry = serde_json::json!({
+                    "name": tool.name(),
+                    "description": tool.description(),
+                    "inputSchema": tool.parameters_schema(),
+                });
+                if needs_approval {
+                    entry["annotations"] = serde_json::json!({ "destructiveHint": true });
+                }
+                entry
+            })
+            .collect();
+        tools.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+        serde_json::json!({ "tools": tools })
+    }
+
+    async fn call_tool(&self, params: serde_json::Value) -> Result<serde_json::Value, RpcError> {
+        let name = params
+            .get("name")
+            .and_then(|n| n.as_str())
+            .ok_or_else(|| RpcError::invalid_params("Missing tool name"))?
+            .to_string();
+        let arguments = params
+            .get("arguments")
+            .cloned()
+            .unwrap_or_else(|| serde_json::json!({}));
+        let tool = self
+            .tools
+            .get(&name)
+            .await
+            .filter(|tool| tool.domain() == ToolDomain::Orchestrator)
+            .ok_or_else(|| RpcError::invalid_params(format!("Unknown tool '{name}'")))?;
+
+        let outcome = self.run_tool(&name, tool, arguments).await;
+        let (text, is_error) = match outcome {
+            Ok(output) => {
+                let sanitized = self.safety.sanitize_tool_output(&name, &output);
+                (sanitized.content, false)
+            }
+            Err(reason) => (reason, true),
+        };
+        self.audit(
+            "mcp_tool_called",
+            None,
+            serde_json::json!({ "tool": name, "success": !is_error }),
+        )
+        .await;
+        Ok(serde_json::json!({
+            "content": [{ "type": "text", "text": text }],
+            "isError": is_error,
+        }))
+    }
+
+    async fn run_tool(
+        &self,
+        name: &str,
+        tool: Arc<dyn crate::tools::Tool>,
+        params: serde_json::Value,
+    ) -> Result<String, String> {
+        // No one is watching an MCP session on our side, so tools that need
+        // approval are blocked exactly as they are in autonomous jobs.
+        let decision =
+            self.tools
+                .approval_decision_for(name, tool.requires_approval(&params), false);
+        if decision.needs_approval {
+            return Err(format!(
+                "Tool '{name}' requires attorney approval and cannot be called over MCP"
+            ));
+        }
+
+        if let Some(config) = tool.rate_limit_config()
+            && let RateLimitResult::Limited { retry_after, .. } = self
+                .tools
+                .rate_limiter()
+                .check_and_record(&self.user_id, name, &config)
+                .await
+        {
+            return Err(format!(
+                "Tool '{name}' is rate limited; retry in {}s",
+                retry_after.as_secs()
+            ));
+        }
+
+        let params = match self.hooks.as_ref() {
+            None => params,
+            Some(hooks) => {
+                let event = HookEvent::ToolCall {
+                    tool_name: name.to_string(),
+                    parameters: params.clone(),
+                    user_id: self.user_id.clone(),
+                    context: "mcp".to_string(),
+                };
+                match hooks.run(&event).await {
+                    Err(HookError::Rejected { reason }) => {
+                        return Err(format!("Blocked by hook: {reason}"));
+                    }
+                    Err(err) => return Err(format!("Blocked by hook failure mode: {err}")),
+                    Ok(HookOutcome::Continue {
+                        modified: Some(new_params),
+                    }) => serde_json::from_str(&new_params).unwrap_or(params),
+                    _ => params,
+                }
+            }
+        };
+
+        let validation = self.safety.validator().validate_tool_params(&params);
+        if !validation.is_valid {
+            let details = validation
+                .errors
+                .iter()
+                .map(|e| format!("{}: {}", e.field, e.message))
+                .collect::<Vec<_>>()
+                .join("; ");
+            return Err(format!("Invalid tool parameters: {details}"));
+        }
+
+        let ctx = JobContext::with_user(&self.user_id, "MCP tool call", name);
+        let timeout = tool.execution_timeout();
+        match tokio::time::timeout(timeout, tool.execute(params, &ctx)).await {
+            Ok(Ok(output)) => serde_json::to_string_pretty(&output.result)
+                .map_err(|e| format!("Failed to serialize result: {e}")),
+            Ok(Err(e)) => Err(e.to_string()),
+            Err(_) => Err(format!("Tool '{name}' timed out after {timeout:?}")),
+        }
+    }
+
+    fn workspace(&self) -> Result<&Arc<Workspace>, RpcError> {
+        self.workspace
+            .as_ref()
+            .ok_or_else(|| RpcError::new(INTERNAL_ERROR, "Workspace not available"))
+    }
+
+    async fn list_resources(&self) -> Result<serde_json::Value, RpcError> {
+        let mut resources = Vec::new();
+        if let Some(workspace) = self.workspace.as_ref() {
+            let entries = workspace
+                .list(crate::legal::policy::matter_root(self.legal.as_ref()))
+                .await
+                .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+            for entry in entries.iter().filter(|entry| entry.is_directory) {
+                let matter_id = entry.name();
+                resources.push(serde_json::json!({
+                    "uri": format!("matter://{matter_id}"),
+                    "name": format!("Matter {matter_id}"),
+                    "description": "Matter metadata, curated context files, and open deadlines",
+                    "mimeType": "application/json",
+                }));
+            }
+        }
+        Ok(serde_json::json!({ "resources": resources }))
+    }
+
+    async fn read_resource(
+        &self,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, RpcError> {
+        let uri = params
+            .get("uri")
+            .and_then(|u| u.as_str())
+            .ok_or_else(|| RpcError::invalid_params("Missing resource uri"))?;
+        let (mime_type, text, matter_id) = if let Some(path) = uri.strip_prefix("workspace:///") {
+            let path = decode_uri_path(path)?;
+            let doc = self
+                .workspace()?
+                .read(&path)
+                .await
+                .map_err(|e| RpcError::invalid_params(e.to_string()))?;
+            ("text/markdown", doc.content, None)
+        } else if let Some(query) = uri.strip_prefix("workspace-search:///") {
+            let query = decode_uri_path(query)?;
+            let results = self
+                .workspace()?
+                .search(&query, SEARCH_RESULT_LIMIT)
+                .await
+                .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+            let hits: Vec<serde_json::Value> = results
+                .into_iter()
+                .map(|hit| {
+                    serde_json::json!({
+                        "uri": format!("workspace:///{}", hit.path),
+                        "path": hit.path,
+                        "score": hit.score,
+                        "content": hit.content,
+                    })
+                })
+                .collect();
+            (
+                "application/json",
+                serde_json::json!({ "query": query, "results": hits }).to_string(),
+                None,
+            )
+        } else if let Some(raw) = uri.strip_prefix("matter://") {
+            let matter_id = crate::legal::policy::sanitize_matter_id(&decode_uri_path(raw)?);
+            if matter_id.is_empty() {
+                return Err(RpcError::invalid_params("Invalid matter ID"));
+            }
+            let context = self.matter_context(&matter_id).await?;
+            ("application/json", context.to_string(), Some(matter_id))
+        } else {
+            return Err(RpcError::invalid_params(format!(
+                "Unsupported resource uri '{uri}'"
+            )));
+        };
+
+        let sanitized = self.safety.sanitize_tool_output("mcp_resource", &text);
+        self.audit(
+            "mcp_resource_read",
+            matter_id.as_deref(),
+            serde_json::json!({ "uri": uri }),
+        )
+        .await;
+        Ok(serde_json::json!({
+            "contents": [{ "uri": uri, "mimeType": mime_type, "text": sanitized.content }],
+        }))
+    }
+
+    async fn matter_context(&self, matter_id: &str) -> Result<serde_json::Value, RpcError> {
+        let workspace = self.workspace()?;
+        let mut legal = self
+            .legal
+            .clone()
+            .filter(|legal| legal.enabled)
+            .ok_or_else(|| RpcError::new(INTERNAL_ERROR, "Legal profile is disabled"))?;
+        legal.active_matter = Some(matter_id.to_string());
+        let context = crate::legal::matter::load_active_matter_prompt_context(workspace, &legal)
+            .await
+            .map_err(|e| RpcError::invalid_params(e.to_string()))?
+            .ok_or_else(|| RpcError::invalid_params(format!("Matter '{matter_id}' not found")))?;
+
+        let mut deadlines = Vec::new();
+        if let Some(store) = self.store.as_ref() {
+            let records = store
+                .list_matter_deadlines(&self.user_id, matter_id)
+                .await
+                .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+            for record in records.iter().filter(|d| d.completed_at.is_none()) {
+                deadlines.push(serde_json::json!({
+                    "title": record.title,
+                    "deadline_type": record.deadline_type.as_str(),
+                    "due_at": record.due_at.to_rfc3339(),
+                    "rule_ref": record.rule_ref,
+                }));
+            }
+        }
+
+        Ok(serde_json::json!({
+            "matter_id": context.matter_id,
+            "client": context.client,
+            "confidentiality": context.confidentiality,
+            "retention": context.retention,
+            "team": context.team,
+            "adversaries": context.adversaries,
+            "jurisdiction": context.jurisdiction,
+            "practice_area": context.practice_area,
+            "opened_date": context.opened_date,
+            "files": context.curated_files.iter().map(|file| serde_json::json!({
+                "name": file.name,
+                "content": file.content,
+                "truncated": file.truncated,
+            })).collect::<Vec<_>>(),
+            "open_deadlines": deadlines,
+        }))
+    }
+
+    async fn audit(&self, event_type: &str, matter_id: Option<&str>, details: serde_json::Value) {
+        match self.store.as_ref() {
+            Some(store) => {
+                crate::legal::audit::record_with_db(
+                    event_type,
+                    MCP_ACTOR,
+                    matter_id,
+                    AuditSeverity::Info,
+                    details,
+                    store.as_ref(),
+                    &self.user_id,
+                )
+                .await;
+            }
+            None => crate::legal::audit::record(event_type, details),
+        }
+    }
+
+    /// Serve newline-delimited JSON-RPC on stdin/stdout until stdin closes.
+    pub async fn serve_stdio(self: Arc<Self>) -> std::io::Result<()> {
+        let (out_tx, mut out_rx) = mpsc::channel::<serde_json::Value>(64);
+        let writer = tokio::spawn(async move {
+            let mut stdout = tokio::io::stdout();
+            while let Some(message) = out_rx.recv().await {
+                let mut line = message.to_string();
+                line.push('\n');
+                stdout.write_all(line.as_bytes()).await?;
+                stdout.flush().await?;
+            }
+            Ok::<_, std::io::Error>(())
+        });
+
+        let mut lines = tokio::io::BufReader::new(tokio::io::stdin()).lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let message = match serde_json::from_str::<serde_json::Value>(&line) {
+                Ok(message) => message,
+                Err(e) => {
+                    let _ = out_tx
+                        .send(rpc_error(
+                            serde_json::Value::Null,
+                            RpcError::new(PARSE_ERROR, e.to_string()),
+                        ))
+                        .await;
+                    continue;
+                }
+            };
+            // Requests run concurrently so a slow tool call does not block pings.
+            let server = Arc::clone(&self);
+            let out_tx = out_tx.clone();
+            tokio::spawn(async move {
+                if let Some(response) = server.handle_message(message).await {
+                    let _ = out_tx.send(response).await;
+                }
+            });
+        }
+        drop(out_tx);
+        writer.await.map_err(std::io::Error::other)?
+    }
+
+    /// Serve the HTTP+SSE transport on `addr`, requiring `token` as a bearer
+    /// token (or `?token=` query parameter) on every request.
+    pub async fn serve_sse(
+        self: Arc<Self>,
+        addr: SocketAddr,
+        token: String,
+    ) -> std::io::Result<()> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        tracing::info!("MCP SSE transport listening on http://{}/sse", addr);
+        axum::serve(listener, sse_router(self, token)).await
+    }
+}
+
+/// Percent-decode the path part of a resource URI.
+fn decode_uri_path(raw: &str) -> Result<String, RpcError> {
+    urlencoding::decode(raw)
+        .map(|decoded| decoded.into_owned())
+        .map_err(|_| RpcError::invalid_params("Resource uri is not valid UTF-8"))
+}
+
+fn resource_templates() -> serde_json::Value {
+    serde_json::json!({
+        "resourceTemplates": [
+            {
+                "uriTemplate": "matter://{matter_id}",
+                "name": "Matter context",
+                "mimeType": "application/json",
+            },
+            {
+                "uriTemplate": "workspace:///{path}",
+                "name": "Workspace document",
+                "mimeType": "text/markdown",
+            },
+            {
+                "uriTemplate": "workspace-search:///{query}",
+                "name": "Workspace search",
+                "mimeType": "application/json",
+            },
+        ],
+    })
+}
+
+struct SseState {
+    server: Arc<McpServer>,
+    token: String,
+    sessions: Mutex<HashMap<Uuid, mpsc::Sender<serde_json::Value>>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SseQuery {
+    session_id: Option<Uuid>,
+    token: Option<String>,
+}
+
+fn sse_router(server: Arc<McpServer>, token: String) -> Router {
+    let state = Arc::new(SseState {
+        server,
+        token,
+        sessions: Mutex::new(HashMap::new()),
+    });
+    Router::new()
+        .route("/sse", get(sse_connect_handler))
+        .route("/messages", post(sse_message_handler))
+        .with_state(state)
+}
+
+fn authorized(state: &SseState, headers: &HeaderMap, query: &SseQuery) -> bool {
+    let presented = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .or(query.token.as_deref());
+    presented.is_some_and(|token| bool::from(token.as_bytes().ct_eq(state.token.as_bytes())))
+}
+
+async fn sse_connect_handler(
+    State(state): State<Arc<SseState>>,
+    headers: HeaderMap,
+    Query(query): Query<SseQuery>,
+) -> Result<Sse<impl futures::Stream<Item = Result<Event, Infallible>> + Send + 'static>, StatusCode>
+{
+    if !authorized(&state, &headers, &query) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    let session_id = Uuid::new_v4();
+    let (tx, rx) = mpsc::channel::<serde_json::Value>(64);
+    state.sessions.lock().await.insert(session_id, tx);
+
+    let endpoint = Event::default()
+        .event("endpoint")
+        .data(format!("/messages?session_id={session_id}"));
+    let messages = tokio_stream::wrappers::ReceiverStream::new(rx)
+        .map(|message| Ok(Event::default().event("message").data(message.to_string())));
+    let stream = futures::stream::once(async move { Ok(endpoint) }).chain(messages);
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+async fn sse_message_handler(
+    State(state): State<Arc<SseState>>,
+    headers: HeaderMap,
+    Query(query): Query<SseQuery>,
+    Json(message): Json<serde_json::Value>,
+) -> StatusCode {
+    if !authorized(&state, &headers, &query) {
+        return StatusCode::UNAUTHORIZED;
+    }
+    let Some(session_id) = query.session_id else {
+        return StatusCode::BAD_REQUEST;
+    };
+    let Some(tx) = state.sessions.lock().await.get(&session_id).cloned() else {
+        return StatusCode::NOT_FOUND;
+    };
+    let state = Arc::clone(&state);
+    tokio::spawn(async move {
+        if let Some(response) = state.server.handle_message(message).await
+            && tx.send(response).await.is_err()
+        {
+            // The client hung up; forget the session.
+            state.sessions.lock().await.remove(&session_id);
+        }
+    });
+    StatusCode::ACCEPTED
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SafetyConfig;
+
+    fn server() -> McpServer {
+        let tools = Arc::new(ToolRegistry::new());
+        tools.register_builtin_tools();
+        let safety = Arc::new(SafetyLayer::new(&SafetyConfig {
+            max_output_length: 100_000,
+            injection_check_enabled: true,
+        }));
+        McpServer::new(tools, safety, "default")
+    }
+
+    #[tokio::test]
+    async fn initialize_and_notifications() {
+        let server = server();
+        let response = server
+            .handle_message(serde_json::json!({
+                "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}
+            }))
+            .await
+            .expect("initialize gets a response");
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["protocolVersion"], PROTOCOL_VERSION);
+        assert!(response["result"]["capabilities"]["resources"].is_object());
+
+        let notification = server
+            .handle_message(serde_json::json!({
+                "jsonrpc": "2.0", "method": "notifications/initialized"
+            }))
+            .await;
+        assert!(notification.is_none());
+
+        let unknown = server
+            .handle_message(serde_json::json!({ "jsonrpc": "2.0", "id": "x", "method": "nope" }))
+            .await
+            .unwrap();
+        assert_eq!(unknown["id"], "x");
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn lists_and_calls_registry_tools() {
+        let server = server();
+        let list = server
+            .dispatch("tools/list", serde_json::Value::Null)
+            .await
+            .unwrap();
+        let tools = list["tools"].as_array().unwrap();
+        let echo = tools
+            .iter()
+            .find(|tool| tool["name"] == "echo")
+            .expect("echo tool listed");
+        assert!(echo["inputSchema"].is_object());
+
+        let result = server
+            .dispatch(
+                "tools/call",
+                serde_json::json!({ "name": "echo", "arguments": { "message": "hello" } }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["isError"], false);
+        assert!(
+            result["content"][0]["text"]
+                .as_str()
+                .unwrap()
+                .contains("hello")
+        );
+
+        let err = server
+            .dispatch("tools/call", serde_json::json!({ "name": "missing" }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_resource_schemes() {
+        let server = server();
+        let err = server
+            .dispatch(
+                "resources/read",
+                serde_json::json!({ "uri": "file:///etc/passwd" }),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, INVALID_PARAMS);
+        let templates = server
+            .dispatch("resources/templates/list", serde_json::Value::Null)
+            .await
+            .unwrap();
+        assert_eq!(templates["resourceTemplates"].as_array().unwrap().len(), 3);
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn reads_matter_and_workspace_resources() {
+        let (db, _tmp) = crate::testing::test_db().await;
+        let workspace = Arc::new(Workspace::new_with_db("default", Arc::clone(&db)));
+        workspace
+            .write(
+                "matters/demo/matter.yaml",
+                "matter_id: demo\nclient: Demo Client\nteam:\n  - Lead Counsel\n\
+                 confidentiality: attorney-client-privileged\nadversaries:\n  - Foo Industries\n\
+                 retention: follow-firm-policy\n",
+            )
+            .await
+            .expect("seed matter metadata");
+        workspace
+            .write("matters/demo/facts.md", "Signed lease on March 1.")
+            .await
+            .expect("seed facts");
+        let mut legal = LegalConfig::resolve(&crate::settings::Settings::default())
+            .expect("default legal config resolves");
+        legal.enabled = true;
+        let server = server()
+            .with_workspace(workspace)
+            .with_store(Arc::clone(&db))
+            .with_legal_config(legal);
+
+        let listed = server
+            .dispatch("resources/list", serde_json::Value::Null)
+            .await
+            .unwrap();
+        assert_eq!(listed["resources"][0]["uri"], "matter://demo");
+
+        let matter = server
+            .dispatch(
+                "resources/read",
+                serde_json::json!({ "uri": "matter://demo" }),
+            )
+            .await
+            .unwrap();
+        let context: serde_json::Value =
+            serde_json::from_str(matter["contents"][0]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(context["client"], "Demo Client");
+        assert!(
+            context["files"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|f| f["name"] == "facts.md")
+        );
+
+        let doc = server
+            .dispatch(
+                "resources/read",
+                serde_json::json!({ "uri": "workspace:///matters/demo/facts.md" }),
+            )
+            .await
+            .unwrap();
+        assert!(
+            doc["contents"][0]["text"]
+                .as_str()
+                .unwrap()
+                .contains("March 1")
+        );
+
+        let query = crate::db::AuditEventQuery {
+            event_type: Some("mcp_resource_read".to_string()),
+            matter_id: Some("demo".to_string()),
+            ..Default::default()
+        };
+        let audit = db
+            .list_audit_events("default", &query, 10, 0)
+            .await
+            .expect("list audit events");
+        assert!(!audit.is_empty());
+    }
+
+    #[tokio::test]
+    async fn sse_transport_requires_token() {
+        use axum::body::Body;
+        use axum::http::Request;
+        use tower::ServiceExt;
+
+        let app = sse_router(Arc::new(server()), "secret".to_string());
+        let unauthenticated = app
+            .clone()
+            .oneshot(Request::get("/sse").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(unauthenticated.status(), StatusCode::UNAUTHORIZED);
+
+        let unknown_session = app
+            .oneshot(
+                Request::post(format!("/messages?session_id={}", Uuid::new_v4()))
+                    .header("authorization", "Bearer secret")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(unknown_session.status(), StatusCode::NOT_FOUND);
+    }
+}
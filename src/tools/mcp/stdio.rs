@@ -0,0 +1,449 @@
+//! Stdio transport for locally spawned MCP servers.
+//!
+//! Most of the MCP ecosystem ships as commands (`npx @modelcontextprotocol/...`,
+//! `uvx mcp-server-...`) that speak newline-delimited JSON-RPC on
+//! stdin/stdout. [`StdioProcess`] owns one such child: it spawns it, runs the
+//! `initialize` handshake, matches responses to requests by id, and keeps the
+//! server alive with a background health check that pings the child and
+//! restarts it with backoff when it dies or stops answering.
+//!
+//! The child is killed when the last handle to the process is dropped.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::{Mutex, oneshot};
+
+use crate::tools::mcp::config::McpServerConfig;
+use crate::tools::mcp::protocol::{InitializeResult, McpRequest, McpResponse};
+use crate::tools::tool::ToolError;
+
+/// How long a single request may wait for the child to respond.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long the `initialize` handshake may take after spawning.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often the health check pings a running server.
+pub const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a health-check ping may take before the server counts as hung.
+const PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest wait between restart attempts of a crashing server.
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(60);
+
+type Pending = Arc<Mutex<HashMap<u64, oneshot::Sender<McpResponse>>>>;
+
+/// One running instance of the child process.
+struct Connection {
+    child: Child,
+    stdin: ChildStdin,
+    pending: Pending,
+}
+
+impl Connection {
+    fn is_alive(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+}
+
+/// A supervised MCP server child process.
+pub struct StdioProcess {
+    config: McpServerConfig,
+    connection: Mutex<Option<Connection>>,
+    next_id: AtomicU64,
+    restarts: AtomicU32,
+    initialize_result: Mutex<InitializeResult>,
+}
+
+impl StdioProcess {
+    /// Spawn the configured command, complete the handshake, and start the
+    /// health check.
+    pub async fn spawn(config: McpServerConfig) -> Result<Arc<Self>, ToolError> {
+        let process = Arc::new(Self {
+            config,
+            connection: Mutex::new(None),
+            next_id: AtomicU64::new(1),
+            restarts: AtomicU32::new(0),
+            initialize_result: Mutex::new(InitializeResult::default()),
+        });
+        {
+            let mut guard = process.connection.lock().await;
+            process.start(&mut guard).await?;
+        }
+        Self::spawn_health_check(Arc::downgrade(&process), HEALTH_CHECK_INTERVAL);
+        Ok(process)
+    }
+
+    /// Number of times the child has been restarted.
+    pub fn restart_count(&self) -> u32 {
+        self.restarts.load(Ordering::Relaxed)
+    }
+
+    /// Result of the most recent `initialize` handshake.
+    pub async fn initialize_result(&self) -> InitializeResult {
+        self.initialize_result.lock().await.clone()
+    }
+
+    /// Whether the child is currently running.
+    #[cfg(test)]
+    async fn is_alive(&self) -> bool {
+        self.connection
+            .lock()
+            .await
+            .as_mut()
+            .is_some_and(Connection::is_alive)
+    }
+
+    /// Send a request and wait for its response. A child that has exited is
+    /// restarted first, so a crash between health checks costs one respawn
+    /// rather than a failed tool call.
+    pub async fn request(&self, request: McpRequest) -> Result<McpResponse, ToolError> {
+        self.request_with_timeout(request, REQUEST_TIMEOUT).await
+    }
+
+    async fn request_with_timeout(
+        &self,
+        mut request: McpRequest,
+        timeout: Duration,
+    ) -> Result<McpResponse, ToolError> {
+        // Ids are assigned here because the handshake and health checks share
+        // the id space with client requests.
+        request.id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let id = request.id;
+        let rx = {
+            let mut guard = self.connection.lock().await;
+            if !guard.as_mut().is_some_and(Connection::is_alive) {
+                tracing::warn!(server = %self.config.name, "MCP server not running; restarting");
+                self.restarts.fetch_add(1, Ordering::Relaxed);
+                self.start(&mut guard).await?;
+            }
+            let connection = guard.as_mut().expect("connection started above");
+            let (tx, rx) = oneshot::channel();
+            connection.pending.lock().await.insert(id, tx);
+            if let Err(e) = write_message(&mut connection.stdin, &request).await {
+                connection.pending.lock().await.remove(&id);
+                return Err(e);
+            }
+            rx
+        };
+
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => Err(ToolError::ExternalService(format!(
+                "MCP server '{}' exited before responding",
+                self.config.name
+            ))),
+            Err(_) => {
+                if let Some(connection) = self.connection.lock().await.as_mut() {
+                    connection.pending.lock().await.remove(&id);
+                }
+                Err(ToolError::Timeout(timeout))
+            }
+        }
+    }
+
+    /// Send a notification (no id, no response).
+    async fn notify(&self, connection: &mut Connection, method: &str) -> Result<(), ToolError> {
+        let message = serde_json::json!({ "jsonrpc": "2.0", "method": method });
+        write_line(&mut connection.stdin, &message.to_string()).await
+    }
+
+    /// Ping the server, restarting it if it has exited or does not answer.
+    pub async fn health_check(&self) -> Result<(), ToolError> {
+        let ping = McpRequest::new(0, "ping", None);
+        match self.request_with_timeout(ping, PING_TIMEOUT).await {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::warn!(
+                    server = %self.config.name,
+                    error = %e,
+                    "MCP server failed health check; restarting"
+                );
+                self.restart().await.map_err(|restart_err| {
+                    ToolError::ExternalService(format!(
+                        "MCP server '{}' unhealthy ({e}) and restart failed: {restart_err}",
+                        self.config.name
+                    ))
+                })
+            }
+        }
+    }
+
+    /// Kill the current child (if any) and start a fresh one.
+    pub async fn restart(&self) -> Result<(), ToolError> {
+        let mut guard = self.connection.lock().await;
+        if let Some(mut old) = guard.take() {
+            let _ = old.child.kill().await;
+        }
+        self.restarts.fetch_add(1, Ordering::Relaxed);
+        self.start(&mut guard).await
+    }
+
+    /// Kill the child. Later requests will start it again.
+    pub async fn shutdown(&self) {
+        if let Some(mut connection) = self.connection.lock().await.take() {
+            let _ = connection.child.kill().await;
+        }
+    }
+
+    async fn start(&self, slot: &mut Option<Connection>) -> Result<(), ToolError> {
+        let mut connection = self.spawn_child()?;
+
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = oneshot::channel();
+        connection.pending.lock().await.insert(id, tx);
+        write_message(&mut connection.stdin, &McpRequest::initialize(id)).await?;
+        let response = match tokio::time::timeout(STARTUP_TIMEOUT, rx).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(_)) => {
+                return Err(ToolError::ExternalService(format!(
+                    "MCP server '{}' exited during initialize",
+                    self.config.name
+                )));
+            }
+            Err(_) => {
+                let _ = connection.child.kill().await;
+                return Err(ToolError::ExternalService(format!(
+                    "MCP server '{}' did not answer initialize within {}s",
+                    self.config.name,
+                    STARTUP_TIMEOUT.as_secs()
+                )));
+            }
+        };
+        if let Some(error) = response.error {
+            let _ = connection.child.kill().await;
+            return Err(ToolError::ExternalService(format!(
+                "MCP initialization error: {} (code {})",
+                error.message, error.code
+            )));
+        }
+        let result: InitializeResult = response
+            .result
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| ToolError::ExternalService(format!("Invalid initialize result: {e}")))?
+            .unwrap_or_default();
+        self.notify(&mut connection, "notifications/initialized")
+            .await?;
+
+        tracing::info!(
+            server = %self.config.name,
+            pid = connection.child.id(),
+            "Started stdio MCP server"
+        );
+        *self.initialize_result.lock().await = result;
+        *slot = Some(connection);
+        Ok(())
+    }
+
+    fn spawn_child(&self) -> Result<Connection, ToolError> {
+        let command = self.config.command.as_deref().ok_or_else(|| {
+            ToolError::ExternalService(format!(
+                "MCP server '{}' has no command configured",
+                self.config.name
+            ))
+        })?;
+        let mut child = Command::new(command)
+            .args(&self.config.args)
+            .envs(&self.config.env)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                ToolError::ExternalService(format!(
+                    "Failed to spawn MCP server '{}' ({command}): {e}",
+                    self.config.name
+                ))
+            })?;
+
+        let stdin = child.stdin.take().ok_or_else(|| {
+            ToolError::ExternalService("Failed to capture MCP server stdin".to_string())
+        })?;
+        let stdout = child.stdout.take().ok_or_else(|| {
+            ToolError::ExternalService("Failed to capture MCP server stdout".to_string())
+        })?;
+        if let Some(stderr) = child.stderr.take() {
+            let name = self.config.name.clone();
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    tracing::debug!(server = %name, "mcp stderr: {}", line);
+                }
+            });
+        }
+
+        let pending: Pending = Arc::new(Mutex::new(HashMap::new()));
+        let reader_pending = Arc::clone(&pending);
+        let name = self.config.name.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let Some(response) = parse_response(&line) else {
+                    // Server-initiated requests and notifications are not
+                    // supported; log and move on.
+                    tracing::debug!(server = %name, "Ignoring MCP message: {}", line);
+                    continue;
+                };
+                if let Some(tx) = reader_pending.lock().await.remove(&response.id) {
+                    let _ = tx.send(response);
+                }
+            }
+            // Dropping the senders wakes every waiter with a closed channel.
+            reader_pending.lock().await.clear();
+        });
+
+        Ok(Connection {
+            child,
+            stdin,
+            pending,
+        })
+    }
+
+    fn spawn_health_check(process: Weak<Self>, interval: Duration) {
+        tokio::spawn(async move {
+            let mut delay = interval;
+            let mut backoff = Duration::ZERO;
+            loop {
+                tokio::time::sleep(delay).await;
+                let Some(process) = process.upgrade() else {
+                    break;
+                };
+                match process.health_check().await {
+                    Ok(()) => {
+                        backoff = Duration::ZERO;
+                        delay = interval;
+                    }
+                    Err(e) => {
+                        backoff = (backoff * 2).clamp(Duration::from_secs(1), MAX_RESTART_BACKOFF);
+                        delay = backoff;
+                        tracing::warn!(
+                            server = %process.config.name,
+                            retry_in_secs = backoff.as_secs(),
+                            error = %e,
+                            "MCP server restart failed"
+                        );
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Parse a line as a response to one of our (numeric-id) requests.
+fn parse_response(line: &str) -> Option<McpResponse> {
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    if !value.get("id").is_some_and(|id| id.is_u64()) || value.get("method").is_some() {
+        return None;
+    }
+    serde_json::from_value(value).ok()
+}
+
+async fn write_message(stdin: &mut ChildStdin, request: &McpRequest) -> Result<(), ToolError> {
+    let line = serde_json::to_string(request)
+        .map_err(|e| ToolError::ExternalService(format!("Failed to encode MCP request: {e}")))?;
+    write_line(stdin, &line).await
+}
+
+async fn write_line(stdin: &mut ChildStdin, line: &str) -> Result<(), ToolError> {
+    let mut bytes = Vec::with_capacity(line.len() + 1);
+    bytes.extend_from_slice(line.as_bytes());
+    bytes.push(b'\n');
+    stdin
+        .write_all(&bytes)
+        .await
+        .map_err(|e| ToolError::ExternalService(format!("Failed to write to MCP server: {e}")))?;
+    stdin
+        .flush()
+        .await
+        .map_err(|e| ToolError::ExternalService(format!("Failed to write to MCP server: {e}")))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// A tiny MCP server in shell: answers every request with an empty result
+    /// carrying the same id, and exits on the `tools/call` for `crash`.
+    fn fake_server(name: &str) -> McpServerConfig {
+        let script = r#"
+while IFS= read -r line; do
+  id=$(printf '%s' "$line" | sed -n 's/.*"id":\([0-9][0-9]*\).*/\1/p')
+  case "$line" in
+    *'"crash"'*) exit 1 ;;
+  esac
+  if [ -n "$id" ]; then
+    case "$line" in
+      *'"tools/list"'*) printf '{"jsonrpc":"2.0","id":%s,"result":{"tools":[{"name":"echo","inputSchema":{"type":"object"},"annotations":{"destructiveHint":true}}]}}\n' "$id" ;;
+      *) printf '{"jsonrpc":"2.0","id":%s,"result":{}}\n' "$id" ;;
+    esac
+  fi
+done
+"#;
+        McpServerConfig::new_stdio(name, "sh", vec!["-c".to_string(), script.to_string()])
+    }
+
+    #[tokio::test]
+    async fn handshake_and_requests_round_trip() {
+        let process = StdioProcess::spawn(fake_server("fake")).await.unwrap();
+        assert!(process.is_alive().await);
+        let response = process
+            .request(McpRequest::list_tools(0))
+            .await
+            .expect("tools/list");
+        assert_eq!(response.result.unwrap()["tools"][0]["name"], "echo");
+        process.health_check().await.expect("healthy");
+        assert_eq!(process.restart_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn crashed_server_is_restarted_on_next_request() {
+        let process = StdioProcess::spawn(fake_server("crashy")).await.unwrap();
+        let crash = McpRequest::call_tool(0, "crash", serde_json::json!({}));
+        assert!(process.request(crash).await.is_err());
+
+        // Wait for the exit to be observable, then the next call respawns.
+        for _ in 0..50 {
+            if !process.is_alive().await {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        process
+            .request(McpRequest::new(0, "ping", None))
+            .await
+            .expect("restarted server answers");
+        assert_eq!(process.restart_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn client_wraps_stdio_tools_with_approval_flags() {
+        use crate::tools::tool::ApprovalRequirement;
+
+        let client = crate::tools::mcp::McpClient::connect_stdio(fake_server("files"))
+            .await
+            .unwrap();
+        let tools = client.create_tools().await.unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name(), "files_echo");
+        assert!(tools[0].requires_sanitization());
+        assert!(matches!(
+            tools[0].requires_approval(&serde_json::json!({})),
+            ApprovalRequirement::UnlessAutoApproved
+        ));
+        client.health_check().await.expect("healthy");
+    }
+
+    #[tokio::test]
+    async fn missing_command_fails_to_spawn() {
+        let config = McpServerConfig::new_stdio("nope", "/nonexistent/mcp-server", Vec::new());
+        assert!(StdioProcess::spawn(config).await.is_err());
+    }
+}
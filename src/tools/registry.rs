@@ -16,14 +16,16 @@ use crate::skills::catalog::SkillCatalog;
 use crate::skills::registry::SkillRegistry;
 use crate::tools::builder::{BuildSoftwareTool, BuilderConfig, LlmSoftwareBuilder};
 use crate::tools::builtin::{
-    ApplyPatchTool, CanLiiSearchTool, CancelJobTool, CorporateComplianceCheckerTool,
-    CourtDeadlineCalculatorTool, CreateJobTool, EchoTool, HttpTool, JobEventsTool, JobPromptTool,
-    JobStatusTool, JsonTool, ListCourtRulesTool, ListDirTool, ListJobsTool, MemoryReadTool,
-    MemorySearchTool, MemoryTreeTool, MemoryWriteTool, OntarioCourtFormTool,
-    OntarioLimitationCalculatorTool, PromptQueue, ReadFileTool, ShellTool, SkillInstallTool,
-    SkillListTool, SkillRemoveTool, SkillSearchTool, TimeTool, ToolActivateTool, ToolAuthTool,
-    ToolInstallTool, ToolListTool, ToolRemoveTool, ToolSearchTool, TrustComplianceCheckerTool,
-    WriteFileTool,
+    ApplyPatchTool, CanLiiSearchTool, CancelJobTool, ClientStatusReportTool, CompareDocumentsTool,
+    ComposeEmailTool, CorporateComplianceCheckerTool, CourtDeadlineCalculatorTool, CreateJobTool,
+    DamagesCalculatorTool, DraftCheckTool, EchoTool, ExtractMatterFactsTool, GenerateDocumentTool,
+    HttpTool, JobEventsTool, JobPromptTool, JobStatusTool, JsonTool, ListCourtRulesTool,
+    ListDirTool, ListJobsTool, MemoryReadTool, MemorySearchTool, MemoryTreeTool, MemoryWriteTool,
+    OntarioCourtFormTool, OntarioLimitationCalculatorTool, PlainLanguageReviewTool,
+    PrecedentSearchTool, PromptQueue, QuoteTestimonyTool, ReadFileTool, RunCodeTool, ShellTool,
+    SkillInstallTool, SkillListTool, SkillRemoveTool, SkillSearchTool, TagDocumentTool, TimeTool,
+    ToolActivateTool, ToolAuthTool, ToolInstallTool, ToolListTool, ToolRemoveTool, ToolSearchTool,
+    TranslateDocumentTool, TrustComplianceCheckerTool, WriteFileTool,
 };
 use crate::tools::rate_limiter::RateLimiter;
 use crate::tools::tool::{ApprovalRequirement, Tool, ToolDomain};
@@ -376,7 +378,11 @@ impl ToolRegistry {
     /// Memory tools require a workspace for persistence. Call this after
     /// `register_builtin_tools()` if you have a workspace available.
     pub fn register_memory_tools(&self, workspace: Arc<Workspace>) {
-        self.register_sync(Arc::new(MemorySearchTool::new(Arc::clone(&workspace))));
+        let mut search_tool = MemorySearchTool::new(Arc::clone(&workspace));
+        if let Some(ref legal) = self.legal {
+            search_tool = search_tool.with_legal_policy(legal.clone());
+        }
+        self.register_sync(Arc::new(search_tool));
         let mut write_tool = MemoryWriteTool::new(Arc::clone(&workspace));
         if let Some(ref legal) = self.legal {
             write_tool = write_tool.with_legal_policy(legal.clone());
@@ -388,6 +394,190 @@ impl ToolRegistry {
         tracing::info!("Registered 4 memory tools");
     }
 
+    /// Register the document translation tool.
+    ///
+    /// Machine translations are written into the workspace beside their
+    /// source and, when a store is available, tracked for certification.
+    pub fn register_translation_tool(
+        &self,
+        workspace: Arc<Workspace>,
+        llm: Arc<dyn LlmProvider>,
+        store: Option<Arc<dyn Database>>,
+    ) {
+        let mut tool = TranslateDocumentTool::new(workspace, llm, store);
+        if let Some(ref legal) = self.legal {
+            tool = tool.with_legal_policy(legal.clone());
+        }
+        self.register_sync(Arc::new(tool));
+        tracing::info!("Registered translate_document tool");
+    }
+
+    /// Register the plain-language review tool.
+    ///
+    /// Thresholds come from the `plain_language` setting when a store is
+    /// available.
+    pub fn register_plain_language_tool(
+        &self,
+        workspace: Arc<Workspace>,
+        llm: Arc<dyn LlmProvider>,
+        store: Option<Arc<dyn Database>>,
+    ) {
+        let mut tool = PlainLanguageReviewTool::new(workspace, llm, store);
+        if let Some(ref legal) = self.legal {
+            tool = tool.with_legal_policy(legal.clone());
+        }
+        self.register_sync(Arc::new(tool));
+        tracing::info!("Registered plain_language_review tool");
+    }
+
+    /// Register the deposition testimony quoting tool.
+    ///
+    /// Reads transcripts ingested through the upload transcript mode and
+    /// cites quoted lines by page and line.
+    pub fn register_transcript_tool(&self, workspace: Arc<Workspace>) {
+        let mut tool = QuoteTestimonyTool::new(workspace);
+        if let Some(ref legal) = self.legal {
+            tool = tool.with_legal_policy(legal.clone());
+        }
+        self.register_sync(Arc::new(tool));
+        tracing::info!("Registered quote_testimony tool");
+    }
+
+    /// Register the draft consistency checking tool.
+    ///
+    /// Reads drafts from the workspace, limited to the active matter when
+    /// the draft sits in a matter folder.
+    pub fn register_draft_check_tool(&self, workspace: Arc<Workspace>) {
+        let mut tool = DraftCheckTool::new(workspace);
+        if let Some(ref legal) = self.legal {
+            tool = tool.with_legal_policy(legal.clone());
+        }
+        self.register_sync(Arc::new(tool));
+        tracing::info!("Registered draft_check tool");
+    }
+
+    /// Register the document comparison tool.
+    ///
+    /// Compares two documents in one matter and, on request, files the
+    /// change memo in the matter's `memos/` folder.
+    pub fn register_compare_documents_tool(
+        &self,
+        workspace: Arc<Workspace>,
+        store: Option<Arc<dyn Database>>,
+    ) {
+        let mut tool = CompareDocumentsTool::new(workspace, store);
+        if let Some(ref legal) = self.legal {
+            tool = tool.with_legal_policy(legal.clone());
+        }
+        self.register_sync(Arc::new(tool));
+        tracing::info!("Registered compare_documents tool");
+    }
+
+    /// Register the damages calculator tool.
+    ///
+    /// Keeps each matter's damages model and summary table under the
+    /// matter's `damages/` folder.
+    pub fn register_damages_tool(&self, workspace: Arc<Workspace>) {
+        let mut tool = DamagesCalculatorTool::new(workspace);
+        if let Some(ref legal) = self.legal {
+            tool = tool.with_legal_policy(legal.clone());
+        }
+        self.register_sync(Arc::new(tool));
+        tracing::info!("Registered damages tool");
+    }
+
+    /// Register the matter fact extraction tool.
+    ///
+    /// Extracted facts go to the fact store, which also regenerates the
+    /// matter's key facts log and chronology.
+    pub fn register_fact_extraction_tool(
+        &self,
+        workspace: Arc<Workspace>,
+        store: Arc<dyn Database>,
+        llm: Arc<dyn LlmProvider>,
+    ) {
+        let mut tool = ExtractMatterFactsTool::new(workspace, store, llm);
+        if let Some(ref legal) = self.legal {
+            tool = tool.with_legal_policy(legal.clone());
+        }
+        self.register_sync(Arc::new(tool));
+        tracing::info!("Registered extract_matter_facts tool");
+    }
+
+    /// Register the document review tagging tool.
+    pub fn register_document_tag_tool(&self, workspace: Arc<Workspace>, store: Arc<dyn Database>) {
+        let mut tool = TagDocumentTool::new(workspace, store);
+        if let Some(ref legal) = self.legal {
+            tool = tool.with_legal_policy(legal.clone());
+        }
+        self.register_sync(Arc::new(tool));
+        tracing::info!("Registered tag_document tool");
+    }
+
+    /// Register the client status report drafting tool.
+    pub fn register_status_report_tool(&self, workspace: Arc<Workspace>, store: Arc<dyn Database>) {
+        let mut tool = ClientStatusReportTool::new(workspace, store);
+        if let Some(ref legal) = self.legal {
+            tool = tool.with_legal_policy(legal.clone());
+        }
+        self.register_sync(Arc::new(tool));
+        tracing::info!("Registered client_status_report tool");
+    }
+
+    /// Register the firm precedent search tool.
+    pub fn register_precedent_tool(&self, workspace: Arc<Workspace>, store: Arc<dyn Database>) {
+        let mut tool = PrecedentSearchTool::new(workspace, store);
+        if let Some(ref legal) = self.legal {
+            tool = tool.with_legal_policy(legal.clone());
+        }
+        self.register_sync(Arc::new(tool));
+        tracing::info!("Registered precedent_search tool");
+    }
+
+    /// Register the template-based document generation tool.
+    pub fn register_generate_document_tool(
+        &self,
+        workspace: Arc<Workspace>,
+        store: Arc<dyn Database>,
+    ) {
+        let mut tool = GenerateDocumentTool::new(workspace, store);
+        if let Some(ref legal) = self.legal {
+            tool = tool.with_legal_policy(legal.clone());
+        }
+        self.register_sync(Arc::new(tool));
+        tracing::info!("Registered generate_document tool");
+    }
+
+    /// Register the matter email drafting tool.
+    ///
+    /// Sending looks the email tool up in this registry at call time, so
+    /// it works with email tools installed after startup.
+    pub fn register_compose_email_tool(self: &Arc<Self>, workspace: Arc<Workspace>) {
+        let mut tool = ComposeEmailTool::new(workspace, Arc::downgrade(self));
+        if let Some(ref legal) = self.legal {
+            tool = tool.with_legal_policy(legal.clone());
+        }
+        self.register_sync(Arc::new(tool));
+        tracing::info!("Registered compose_email tool");
+    }
+
+    /// Register the `run_code` script tool.
+    ///
+    /// Scripts run in throwaway containers started by the job manager, so
+    /// this is only available when the sandbox is enabled.
+    pub fn register_run_code_tool(
+        &self,
+        job_manager: Arc<ContainerJobManager>,
+        workspace: Arc<Workspace>,
+    ) {
+        let mut tool = RunCodeTool::new(job_manager, workspace);
+        if let Some(ref legal) = self.legal {
+            tool = tool.with_legal_policy(legal.clone());
+        }
+        self.register_sync(Arc::new(tool));
+        tracing::info!("Registered run_code tool");
+    }
+
     /// Register job management tools.
     ///
     /// Job tools allow the LLM to create, list, check status, and cancel jobs.
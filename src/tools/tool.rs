@@ -287,6 +287,11 @@ pub fn require_param<'a>(
         .ok_or_else(|| ToolError::InvalidParameters(format!("missing '{}' parameter", name)))
 }
 
+/// Map a storage error into [`ToolError::ExecutionFailed`].
+pub fn db_err(err: impl std::fmt::Display) -> ToolError {
+    ToolError::ExecutionFailed(err.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
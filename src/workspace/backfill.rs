@@ -0,0 +1,384 @@
+//! Background embedding backfill.
+//!
+//! Chunks are written without an embedding when no provider is configured
+//! or the provider call fails during indexing. This worker drains those
+//! chunks in bounded batches, throttled against the provider, and records
+//! progress per user so the web status endpoint can report how much of the
+//! workspace is searchable semantically.
+//!
+//! ```text
+//! ┌──────────────────────────────────────────────────┐
+//! │                 Backfill Pass                     │
+//! │                                                   │
+//! │  1. Fetch a batch of chunks without embeddings    │
+//! │  2. Embed with bounded concurrency + rate limit   │
+//! │  3. Persist progress after every batch            │
+//! │  4. Repeat until drained; then idle and re-check  │
+//! └──────────────────────────────────────────────────┘
+//! ```
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::db::Database;
+use crate::error::WorkspaceError;
+use crate::workspace::Workspace;
+
+/// Settings key holding the persisted [`EmbeddingBackfillProgress`].
+pub const EMBEDDING_BACKFILL_SETTING: &str = "workspace.embedding_backfill";
+
+/// Configuration for the embedding backfill worker.
+#[derive(Debug, Clone)]
+pub struct EmbeddingBackfillConfig {
+    /// Chunks fetched per batch.
+    pub batch_size: usize,
+    /// Maximum concurrent embedding requests.
+    pub concurrency: usize,
+    /// Provider requests allowed per minute (0 = unlimited).
+    pub requests_per_minute: u32,
+    /// Delay between checks once the backlog is drained.
+    pub idle_interval: Duration,
+}
+
+impl Default for EmbeddingBackfillConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 32,
+            concurrency: 4,
+            requests_per_minute: 300,
+            idle_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Lifecycle state of the backfill worker.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackfillState {
+    /// No pending work (or the worker has not run yet).
+    #[default]
+    Idle,
+    Running,
+    /// The last pass stopped because every request in a batch failed.
+    Failed,
+}
+
+/// Progress persisted per user under [`EMBEDDING_BACKFILL_SETTING`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddingBackfillProgress {
+    #[serde(default)]
+    pub state: BackfillState,
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Chunks embedded by the worker across all runs.
+    #[serde(default)]
+    pub embedded_total: u64,
+    /// Embedding attempts that failed across all runs.
+    #[serde(default)]
+    pub failed_total: u64,
+    #[serde(default)]
+    pub last_batch_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub last_completed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+impl EmbeddingBackfillProgress {
+    /// Load persisted progress, defaulting when absent or unreadable.
+    pub async fn load(store: &dyn Database, user_id: &str) -> Self {
+        match store.get_setting(user_id, EMBEDDING_BACKFILL_SETTING).await {
+            Ok(Some(value)) => serde_json::from_value(value).unwrap_or_default(),
+            Ok(None) => Self::default(),
+            Err(e) => {
+                tracing::debug!("Failed to load embedding backfill progress: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    async fn save(&self, store: &dyn Database, user_id: &str) {
+        let value = match serde_json::to_value(self) {
+            Ok(value) => value,
+            Err(e) => {
+                tracing::warn!("Failed to serialize embedding backfill progress: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = store
+            .set_setting(user_id, EMBEDDING_BACKFILL_SETTING, &value)
+            .await
+        {
+            tracing::warn!("Failed to persist embedding backfill progress: {}", e);
+        }
+    }
+}
+
+/// Summary of a single drain pass.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BackfillPassReport {
+    pub embedded: usize,
+    pub failed: usize,
+    /// True when the pass stopped early because a whole batch failed.
+    pub aborted: bool,
+}
+
+/// Spaces provider requests evenly to honour a per-minute budget.
+struct RequestPacer {
+    period: Option<Duration>,
+    next: Mutex<Instant>,
+}
+
+impl RequestPacer {
+    fn new(requests_per_minute: u32) -> Self {
+        let period =
+            (requests_per_minute > 0).then(|| Duration::from_secs(60) / requests_per_minute);
+        Self {
+            period,
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    async fn wait(&self) {
+        let Some(period) = self.period else {
+            return;
+        };
+        let slot = {
+            let mut next = self.next.lock().await;
+            let slot = (*next).max(Instant::now());
+            *next = slot + period;
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+/// Drains chunks that are missing embeddings for one workspace.
+pub struct EmbeddingBackfillWorker {
+    workspace: Arc<Workspace>,
+    store: Option<Arc<dyn Database>>,
+    config: EmbeddingBackfillConfig,
+    pacer: RequestPacer,
+}
+
+impl EmbeddingBackfillWorker {
+    pub fn new(workspace: Arc<Workspace>, config: EmbeddingBackfillConfig) -> Self {
+        Self {
+            pacer: RequestPacer::new(config.requests_per_minute),
+            workspace,
+            store: None,
+            config,
+        }
+    }
+
+    /// Persist progress to the settings store.
+    pub fn with_store(mut self, store: Arc<dyn Database>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    async fn load_progress(&self) -> EmbeddingBackfillProgress {
+        match self.store.as_ref() {
+            Some(store) => {
+                EmbeddingBackfillProgress::load(store.as_ref(), self.workspace.user_id()).await
+            }
+            None => EmbeddingBackfillProgress::default(),
+        }
+    }
+
+    async fn save_progress(&self, progress: &EmbeddingBackfillProgress) {
+        if let Some(store) = self.store.as_ref() {
+            progress
+                .save(store.as_ref(), self.workspace.user_id())
+                .await;
+        }
+    }
+
+    /// Embed pending chunks until none remain (or a whole batch fails).
+    ///
+    /// Chunks that fail are skipped for the rest of the pass so a single
+    /// bad chunk cannot stall the queue; they are retried on the next pass.
+    pub async fn run_pass(&self) -> Result<BackfillPassReport, WorkspaceError> {
+        let mut report = BackfillPassReport::default();
+        if !self.workspace.has_embeddings() {
+            return Ok(report);
+        }
+
+        let batch_size = self.config.batch_size.max(1);
+        let concurrency = self.config.concurrency.max(1);
+        let mut progress = self.load_progress().await;
+        let mut skipped: HashSet<Uuid> = HashSet::new();
+        let mut started = false;
+
+        loop {
+            let batch: Vec<_> = self
+                .workspace
+                .chunks_without_embeddings(batch_size + skipped.len())
+                .await?
+                .into_iter()
+                .filter(|chunk| !skipped.contains(&chunk.id))
+                .take(batch_size)
+                .collect();
+            if batch.is_empty() {
+                break;
+            }
+            if !started {
+                started = true;
+                progress.state = BackfillState::Running;
+                progress.model = self.workspace.embedding_model().map(str::to_string);
+                progress.last_error = None;
+            }
+
+            let results: Vec<_> = futures::stream::iter(batch)
+                .map(|chunk| async move {
+                    self.pacer.wait().await;
+                    let result = self.workspace.embed_chunk(chunk.id, &chunk.content).await;
+                    (chunk.id, result)
+                })
+                .buffer_unordered(concurrency)
+                .collect()
+                .await;
+
+            let mut batch_embedded = 0usize;
+            for (chunk_id, result) in results {
+                match result {
+                    Ok(()) => batch_embedded += 1,
+                    Err(e) => {
+                        tracing::warn!("Failed to embed chunk {}: {}", chunk_id, e);
+                        progress.last_error = Some(e.to_string());
+                        skipped.insert(chunk_id);
+                        report.failed += 1;
+                        progress.failed_total += 1;
+                    }
+                }
+            }
+            report.embedded += batch_embedded;
+            progress.embedded_total += batch_embedded as u64;
+            progress.last_batch_at = Some(Utc::now());
+
+            if batch_embedded == 0 {
+                report.aborted = true;
+                progress.state = BackfillState::Failed;
+                self.save_progress(&progress).await;
+                return Ok(report);
+            }
+            self.save_progress(&progress).await;
+        }
+
+        if started {
+            progress.state = BackfillState::Idle;
+            progress.last_completed_at = Some(Utc::now());
+            self.save_progress(&progress).await;
+        }
+        Ok(report)
+    }
+
+    /// Run passes forever, idling between them once the backlog is drained.
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match self.run_pass().await {
+                    Ok(report) if report.embedded > 0 || report.failed > 0 => {
+                        tracing::info!(
+                            embedded = report.embedded,
+                            failed = report.failed,
+                            aborted = report.aborted,
+                            "Embedding backfill pass finished"
+                        );
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        tracing::warn!("Embedding backfill pass failed: {}", e);
+                    }
+                }
+                tokio::time::sleep(self.config.idle_interval).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn pacer_spaces_requests() {
+        let pacer = RequestPacer::new(60);
+        let start = Instant::now();
+        pacer.wait().await;
+        pacer.wait().await;
+        pacer.wait().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn unlimited_pacer_never_waits() {
+        let pacer = RequestPacer::new(0);
+        for _ in 0..100 {
+            pacer.wait().await;
+        }
+    }
+
+    #[test]
+    fn progress_round_trips_with_missing_fields() {
+        let progress: EmbeddingBackfillProgress =
+            serde_json::from_value(serde_json::json!({ "embedded_total": 5 }))
+                .expect("deserialize");
+        assert_eq!(progress.state, BackfillState::Idle);
+        assert_eq!(progress.embedded_total, 5);
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn run_pass_embeds_pending_chunks_and_persists_progress() {
+        use crate::workspace::MockEmbeddings;
+
+        let (db, _tmp) = crate::testing::test_db().await;
+        // Write without a provider so chunks land un-embedded.
+        let plain = Workspace::new_with_db("test-user", Arc::clone(&db));
+        plain
+            .write("notes/a.md", "Alpha clause text")
+            .await
+            .expect("write a");
+        plain
+            .write("notes/b.md", "Beta clause text")
+            .await
+            .expect("write b");
+        let before = plain.storage_usage("").await.expect("usage");
+        assert!(before.chunk_count >= 2);
+        assert_eq!(before.embedded_chunk_count, 0);
+
+        let workspace = Arc::new(
+            Workspace::new_with_db("test-user", Arc::clone(&db))
+                .with_embeddings(Arc::new(MockEmbeddings::new(1536))),
+        );
+        let worker = EmbeddingBackfillWorker::new(
+            Arc::clone(&workspace),
+            EmbeddingBackfillConfig {
+                batch_size: 1,
+                requests_per_minute: 0,
+                ..Default::default()
+            },
+        )
+        .with_store(Arc::clone(&db));
+        let report = worker.run_pass().await.expect("pass");
+        assert_eq!(report.embedded as u64, before.chunk_count);
+        assert!(!report.aborted);
+
+        let after = workspace.storage_usage("").await.expect("usage");
+        assert_eq!(after.embedded_chunk_count, after.chunk_count);
+
+        let progress = EmbeddingBackfillProgress::load(db.as_ref(), "test-user").await;
+        assert_eq!(progress.state, BackfillState::Idle);
+        assert_eq!(progress.embedded_total, before.chunk_count);
+        assert!(progress.last_completed_at.is_some());
+    }
+}
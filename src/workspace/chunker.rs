@@ -113,6 +113,30 @@ pub fn chunk_document(content: &str, config: ChunkConfig) -> Vec<String> {
     chunks
 }
 
+/// Byte range of `chunk` within `content`.
+///
+/// Chunks are whitespace-normalized copies of the source, so this matches
+/// word-by-word rather than by substring. Returns `None` when the document
+/// has changed since the chunk was indexed.
+pub fn locate_chunk(content: &str, chunk: &str) -> Option<std::ops::Range<usize>> {
+    let needle: Vec<&str> = chunk.split_whitespace().collect();
+    if needle.is_empty() {
+        return None;
+    }
+    let words: Vec<(usize, &str)> = content
+        .split_whitespace()
+        .map(|word| (word.as_ptr() as usize - content.as_ptr() as usize, word))
+        .collect();
+    words
+        .windows(needle.len())
+        .find(|window| window.iter().map(|(_, w)| *w).eq(needle.iter().copied()))
+        .map(|window| {
+            let (start, _) = window[0];
+            let (last_start, last) = window[window.len() - 1];
+            start..last_start + last.len()
+        })
+}
+
 /// Split content by paragraphs first, then chunk.
 ///
 /// This is better for preserving semantic boundaries.
@@ -312,4 +336,13 @@ mod tests {
         assert_eq!(chunks.len(), 1);
         assert_eq!(chunks[0].split_whitespace().count(), 12);
     }
+
+    #[test]
+    fn test_locate_chunk_ignores_whitespace_normalization() {
+        let content = "# Lease\n\nRent  is due\non the first.\n\nLate fees apply.";
+        let range = locate_chunk(content, "Rent is due on the first.").expect("located");
+        assert_eq!(&content[range], "Rent  is due\non the first.");
+        assert!(locate_chunk(content, "Rent is waived").is_none());
+        assert!(locate_chunk(content, "   ").is_none());
+    }
 }
@@ -157,6 +157,82 @@ impl MemoryChunk {
     }
 }
 
+/// A chunk resolved from a citation anchor, with the path of its document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CitedChunk {
+    /// Chunk ID (the anchor target).
+    pub chunk_id: Uuid,
+    /// Parent document ID.
+    pub document_id: Uuid,
+    /// Workspace path of the parent document.
+    pub path: String,
+    /// Position in the document (0-based).
+    pub chunk_index: i32,
+    /// Chunk text content.
+    pub content: String,
+}
+
+/// A deleted document held in the workspace trash until restored or purged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedDocument {
+    /// Trash entry ID (not the original document ID).
+    pub id: Uuid,
+    pub user_id: String,
+    pub agent_id: Option<Uuid>,
+    /// Path the document had when it was deleted.
+    pub path: String,
+    /// Content exactly as stored (ciphertext for encrypted matter files).
+    pub content: String,
+    pub metadata: serde_json::Value,
+    /// Who deleted the document.
+    pub deleted_by: String,
+    pub document_created_at: DateTime<Utc>,
+    pub document_updated_at: DateTime<Utc>,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// One recorded revision of a workspace document's content.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentRevision {
+    pub id: Uuid,
+    pub document_id: Uuid,
+    /// 1-based revision number, increasing with each content change.
+    pub revision: i64,
+    /// Content as written in this revision (ciphertext for encrypted matter files).
+    pub content: String,
+    /// Hex SHA-256 of the stored content.
+    pub content_hash: String,
+    /// Line diff against the previous revision; `None` for encrypted content.
+    pub diff: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Aggregate storage footprint of workspace documents under a path prefix.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceStorageUsage {
+    /// Number of documents.
+    pub document_count: u64,
+    /// Stored document bytes (ciphertext for encrypted files).
+    pub document_bytes: u64,
+    /// Number of search chunks.
+    pub chunk_count: u64,
+    /// Bytes of chunk text held in the search index.
+    pub chunk_bytes: u64,
+    /// Chunks that carry an embedding vector.
+    pub embedded_chunk_count: u64,
+    /// Documents stored as opaque encrypted envelopes.
+    pub binary_count: u64,
+    /// Bytes held in encrypted envelopes (subset of `document_bytes`).
+    pub binary_bytes: u64,
+}
+
+impl WorkspaceStorageUsage {
+    /// Total bytes attributable to the prefix (documents plus index).
+    pub fn total_bytes(&self) -> u64 {
+        self.document_bytes.saturating_add(self.chunk_bytes)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -218,4 +294,15 @@ mod tests {
         };
         assert_eq!(entry.name(), "alpha");
     }
+
+    #[test]
+    fn test_storage_usage_total_bytes() {
+        let usage = WorkspaceStorageUsage {
+            document_bytes: 1_000,
+            chunk_bytes: 250,
+            binary_bytes: 400,
+            ..Default::default()
+        };
+        assert_eq!(usage.total_bytes(), 1_250);
+    }
 }
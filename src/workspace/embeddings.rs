@@ -25,6 +25,9 @@ pub enum EmbeddingError {
 
     #[error("Text too long: {length} > {max}")]
     TextTooLong { length: usize, max: usize },
+
+    #[error("Local model error: {0}")]
+    LocalModel(String),
 }
 
 impl From<reqwest::Error> for EmbeddingError {
@@ -0,0 +1,440 @@
+//! Git mirror of the workspace.
+//!
+//! Exports the whole memory tree into a local bare repository (and
+//! optionally pushes it to a remote), one commit per change batch, so
+//! changes can be reviewed with ordinary git tooling: `git log -p` shows
+//! what the agent changed overnight. Content is mirrored exactly as stored,
+//! so encrypted matter files stay encrypted in the repository.
+//!
+//! Attribution: paths a user changed through the memory API are recorded
+//! with [`GitMirror::record_user_change`] and committed under that user;
+//! every other change in the batch is committed under the agent.
+//!
+//! Runs the `git` binary; no libgit2 dependency.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::process::Command;
+
+use crate::workspace::Workspace;
+
+const AGENT_NAME: &str = "cLawyer agent";
+const AGENT_EMAIL: &str = "agent@clawyer.local";
+/// Paths listed in a commit body before it is summarized.
+const MAX_LISTED_PATHS: usize = 50;
+
+/// Errors from mirroring the workspace to git.
+#[derive(Debug, thiserror::Error)]
+pub enum GitMirrorError {
+    #[error("failed to run git: {0}")]
+    Spawn(#[from] std::io::Error),
+
+    #[error("git {command} failed: {stderr}")]
+    Git { command: String, stderr: String },
+
+    #[error("workspace error: {0}")]
+    Workspace(#[from] crate::error::WorkspaceError),
+
+    #[error("invalid path in git tree: {0}")]
+    InvalidPath(String),
+}
+
+/// Who a mirror commit is attributed to.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MirrorAuthor {
+    Agent,
+    User(String),
+}
+
+impl MirrorAuthor {
+    /// `--author` value for this author.
+    fn git_author(&self) -> String {
+        match self {
+            Self::Agent => format!("{AGENT_NAME} <{AGENT_EMAIL}>"),
+            Self::User(user_id) => format!("{user_id} <{user_id}@users.clawyer.local>"),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Self::Agent => "agent".to_string(),
+            Self::User(user_id) => format!("user {user_id}"),
+        }
+    }
+}
+
+/// A commit written by [`GitMirror::sync`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MirrorCommit {
+    pub sha: String,
+    pub author: String,
+    pub paths: Vec<String>,
+}
+
+/// Mirrors a workspace into a bare git repository.
+pub struct GitMirror {
+    repo: PathBuf,
+    remote: Option<String>,
+    branch: String,
+    /// Paths changed through the memory API since the last sync, by user.
+    pending_users: std::sync::Mutex<BTreeMap<String, String>>,
+    /// Serializes syncs and imports; both share the repository index.
+    sync_lock: tokio::sync::Mutex<()>,
+}
+
+impl GitMirror {
+    pub fn new(repo: PathBuf, remote: Option<String>, branch: String) -> Self {
+        Self {
+            repo,
+            remote,
+            branch,
+            pending_users: std::sync::Mutex::new(BTreeMap::new()),
+            sync_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn repo_path(&self) -> &Path {
+        &self.repo
+    }
+
+    pub fn remote(&self) -> Option<&str> {
+        self.remote.as_deref()
+    }
+
+    pub fn branch(&self) -> &str {
+        &self.branch
+    }
+
+    /// Create the bare repository if it does not exist yet.
+    pub async fn init(&self) -> Result<(), GitMirrorError> {
+        if self.repo.join("HEAD").exists() {
+            return Ok(());
+        }
+        tokio::fs::create_dir_all(&self.repo).await?;
+        let output = Command::new("git")
+            .arg("init")
+            .arg("--bare")
+            .arg("--quiet")
+            .arg(format!("--initial-branch={}", self.branch))
+            .arg(&self.repo)
+            .output()
+            .await?;
+        check_output("init", output).map(|_| ())
+    }
+
+    /// Attribute the next commit touching `path` to `user_id`.
+    pub fn record_user_change(&self, user_id: &str, path: &str) {
+        if let Ok(mut pending) = self.pending_users.lock() {
+            pending.insert(path.to_string(), user_id.to_string());
+        }
+    }
+
+    /// Paths with a recorded user change not yet committed.
+    pub fn pending_user_changes(&self) -> usize {
+        self.pending_users.lock().map(|p| p.len()).unwrap_or(0)
+    }
+
+    /// Current branch head, or `None` before the first commit.
+    pub async fn head(&self) -> Result<Option<String>, GitMirrorError> {
+        let output = self
+            .git(None)
+            .args(["rev-parse", "--verify", "--quiet"])
+            .arg(format!("refs/heads/{}^{{commit}}", self.branch))
+            .output()
+            .await?;
+        if !output.status.success() {
+            return Ok(None);
+        }
+        Ok(Some(
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ))
+    }
+
+    /// Export the workspace and commit whatever changed since the last sync:
+    /// one commit per user with recorded changes, then one for the agent.
+    /// Pushes to the remote when one is configured.
+    pub async fn sync(&self, workspace: &Workspace) -> Result<Vec<MirrorCommit>, GitMirrorError> {
+        let _guard = self.sync_lock.lock().await;
+        self.init().await?;
+
+        let work_tree =
+            std::env::temp_dir().join(format!("clawyer-git-mirror-{}", uuid::Uuid::new_v4()));
+        tokio::fs::create_dir_all(&work_tree).await?;
+        let result = self.commit_work_tree(workspace, &work_tree).await;
+        let _ = tokio::fs::remove_dir_all(&work_tree).await;
+        result
+    }
+
+    async fn commit_work_tree(
+        &self,
+        workspace: &Workspace,
+        tree: &Path,
+    ) -> Result<Vec<MirrorCommit>, GitMirrorError> {
+        for path in workspace.list_all().await? {
+            let Some(target) = safe_join(tree, &path) else {
+                tracing::warn!(path, "git mirror: skipping unsafe workspace path");
+                continue;
+            };
+            let doc = workspace.read_stored(&path).await?;
+            if let Some(parent) = target.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::write(&target, doc.content.as_bytes()).await?;
+        }
+
+        self.run(Some(tree), &["add", "--all", "."]).await?;
+        let changed: BTreeSet<String> = self
+            .run(Some(tree), &["diff", "--cached", "--name-only", "-z"])
+            .await?
+            .split('\0')
+            .filter(|p| !p.is_empty())
+            .map(str::to_string)
+            .collect();
+        if changed.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let pending = self
+            .pending_users
+            .lock()
+            .map(|mut p| std::mem::take(&mut *p))
+            .unwrap_or_default();
+        let mut batches: BTreeMap<MirrorAuthor, Vec<String>> = BTreeMap::new();
+        for path in &changed {
+            let author = pending
+                .get(path)
+                .map(|user| MirrorAuthor::User(user.clone()))
+                .unwrap_or(MirrorAuthor::Agent);
+            batches.entry(author).or_default().push(path.clone());
+        }
+        // User edits first, so the agent commit shows what it changed on top.
+        let mut ordered: Vec<_> = batches.into_iter().collect();
+        ordered.sort_by_key(|(author, _)| *author == MirrorAuthor::Agent);
+
+        let mut commits = Vec::new();
+        for (author, paths) in ordered {
+            let message = commit_message(&author, &paths);
+            let output = self
+                .git(Some(tree))
+                .args(["-c", &format!("user.name={AGENT_NAME}")])
+                .args(["-c", &format!("user.email={AGENT_EMAIL}")])
+                .args(["-c", "commit.gpgsign=false"])
+                .args(["commit", "--quiet", "--no-verify"])
+                .arg(format!("--author={}", author.git_author()))
+                .args(["-m", &message, "--"])
+                .args(&paths)
+                .output()
+                .await?;
+            check_output("commit", output)?;
+            let sha = self.head().await?.unwrap_or_default();
+            commits.push(MirrorCommit {
+                sha,
+                author: author.label(),
+                paths,
+            });
+        }
+
+        if let Some(remote) = self.remote.as_deref() {
+            let refspec = format!("refs/heads/{0}:refs/heads/{0}", self.branch);
+            self.run(None, &["push", "--quiet", remote, &refspec])
+                .await?;
+        }
+        Ok(commits)
+    }
+
+    /// Write every file at `rev` (default: the mirror branch) back into the
+    /// workspace as stored content. Documents missing from the tree are left
+    /// alone. Returns the number of files written.
+    pub async fn import(
+        &self,
+        workspace: &Workspace,
+        rev: Option<&str>,
+    ) -> Result<usize, GitMirrorError> {
+        let _guard = self.sync_lock.lock().await;
+        let rev = rev.unwrap_or(&self.branch);
+        let listing = self
+            .run(None, &["ls-tree", "-r", "-z", "--name-only", rev])
+            .await?;
+        let mut imported = 0;
+        for path in listing.split('\0').filter(|p| !p.is_empty()) {
+            if path.split('/').any(|part| part == ".." || part.is_empty()) {
+                return Err(GitMirrorError::InvalidPath(path.to_string()));
+            }
+            let object = format!("{rev}:{path}");
+            let content = self.run(None, &["show", &object]).await?;
+            workspace.write_stored(path, &content).await?;
+            imported += 1;
+        }
+        Ok(imported)
+    }
+
+    /// Sync every `interval` until the process exits.
+    pub fn spawn_sync_loop(
+        self: Arc<Self>,
+        workspace: Arc<Workspace>,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                match self.sync(&workspace).await {
+                    Ok(commits) if !commits.is_empty() => {
+                        tracing::info!(commits = commits.len(), "git mirror: synced workspace");
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("git mirror: sync failed: {e}"),
+                }
+            }
+        })
+    }
+
+    fn git(&self, work_tree: Option<&Path>) -> Command {
+        let mut cmd = Command::new("git");
+        cmd.arg("--git-dir").arg(&self.repo);
+        if let Some(work_tree) = work_tree {
+            cmd.arg("--work-tree").arg(work_tree).current_dir(work_tree);
+        }
+        cmd
+    }
+
+    async fn run(&self, work_tree: Option<&Path>, args: &[&str]) -> Result<String, GitMirrorError> {
+        let output = self.git(work_tree).args(args).output().await?;
+        check_output(args.first().copied().unwrap_or("git"), output)
+    }
+}
+
+fn check_output(command: &str, output: std::process::Output) -> Result<String, GitMirrorError> {
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(GitMirrorError::Git {
+            command: command.to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        })
+    }
+}
+
+/// `root/path`, or `None` if `path` could escape `root`.
+fn safe_join(root: &Path, path: &str) -> Option<PathBuf> {
+    let relative = Path::new(path);
+    relative
+        .components()
+        .all(|c| matches!(c, std::path::Component::Normal(_)))
+        .then(|| root.join(relative))
+}
+
+fn commit_message(author: &MirrorAuthor, paths: &[String]) -> String {
+    let noun = if paths.len() == 1 { "file" } else { "files" };
+    let mut message = format!(
+        "Workspace sync: {} {noun} changed by {}\n\n",
+        paths.len(),
+        author.label()
+    );
+    for path in paths.iter().take(MAX_LISTED_PATHS) {
+        message.push_str(path);
+        message.push('\n');
+    }
+    if paths.len() > MAX_LISTED_PATHS {
+        message.push_str(&format!(
+            "... and {} more\n",
+            paths.len() - MAX_LISTED_PATHS
+        ));
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_join_rejects_escaping_paths() {
+        let root = Path::new("/tmp/mirror");
+        assert_eq!(
+            safe_join(root, "matters/acme/notes.md"),
+            Some(root.join("matters/acme/notes.md"))
+        );
+        assert!(safe_join(root, "../etc/passwd").is_none());
+        assert!(safe_join(root, "/etc/passwd").is_none());
+    }
+
+    #[cfg(feature = "libsql")]
+    #[tokio::test]
+    async fn sync_attributes_commits_and_import_round_trips() {
+        if std::process::Command::new("git")
+            .arg("--version")
+            .output()
+            .is_err()
+        {
+            return;
+        }
+        let (db, _tmp) = crate::testing::test_db().await;
+        let workspace = Workspace::new_with_db("default", Arc::clone(&db));
+        let repo_dir = tempfile::tempdir().expect("repo dir");
+        let mirror = GitMirror::new(repo_dir.path().join("ws.git"), None, "main".to_string());
+
+        workspace
+            .write("MEMORY.md", "Prefers short memos")
+            .await
+            .unwrap();
+        workspace
+            .write("matters/acme/notes.md", "Call client")
+            .await
+            .unwrap();
+        mirror.record_user_change("alice", "matters/acme/notes.md");
+
+        let commits = mirror.sync(&workspace).await.expect("first sync");
+        let summary: Vec<_> = commits
+            .iter()
+            .map(|c| (c.author.as_str(), c.paths.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("user alice", vec!["matters/acme/notes.md".to_string()]),
+                ("agent", vec!["MEMORY.md".to_string()]),
+            ]
+        );
+        let log = mirror
+            .run(None, &["log", "--format=%an", "main"])
+            .await
+            .unwrap();
+        assert_eq!(log, "cLawyer agent\nalice\n");
+        assert!(mirror.sync(&workspace).await.unwrap().is_empty());
+
+        workspace.delete("MEMORY.md").await.unwrap();
+        let commits = mirror.sync(&workspace).await.expect("sync deletion");
+        assert_eq!(commits[0].paths, vec!["MEMORY.md".to_string()]);
+
+        let (db2, _tmp2) = crate::testing::test_db().await;
+        let restored = Workspace::new_with_db("default", db2);
+        let first = mirror
+            .run(None, &["rev-list", "--max-parents=0", "main"])
+            .await
+            .unwrap();
+        let imported = mirror
+            .import(&restored, Some("main"))
+            .await
+            .expect("import head");
+        assert_eq!(imported, 1);
+        assert!(!restored.exists("MEMORY.md").await.unwrap());
+        let imported = mirror
+            .import(&restored, Some(first.trim()))
+            .await
+            .expect("import first commit");
+        assert_eq!(imported, 1);
+        assert_eq!(
+            restored
+                .read("matters/acme/notes.md")
+                .await
+                .unwrap()
+                .content,
+            "Call client"
+        );
+    }
+}
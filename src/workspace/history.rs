@@ -0,0 +1,146 @@
+//! Document revision hashes and diffs.
+//!
+//! Every `update_document` records a revision holding the stored content,
+//! its SHA-256, and a line diff against the previous revision. Diffs are
+//! only stored for plaintext; encrypted matter content gets its diff
+//! computed after decryption when history is read.
+
+use sha2::{Digest, Sha256};
+
+/// Above this many comparisons a diff falls back to replacing everything
+/// instead of building the quadratic LCS table.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Hex SHA-256 of stored document content.
+pub fn content_hash(content: &str) -> String {
+    let digest = Sha256::digest(content.as_bytes());
+    digest.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Whether stored `content` satisfies an `If-Match` ETag. Quotes and a
+/// weak `W/` prefix are ignored; `*` matches any existing content.
+pub fn etag_matches(if_match: &str, content: &str) -> bool {
+    let current = content_hash(content);
+    if_match
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/").trim_matches('"') == current)
+}
+
+/// Diff to store for a revision, or `None` when either side is encrypted.
+pub fn stored_revision_diff(previous: &str, current: &str) -> Option<String> {
+    if crate::legal::workspace_crypto::is_encrypted_payload(previous)
+        || crate::legal::workspace_crypto::is_encrypted_payload(current)
+    {
+        return None;
+    }
+    Some(line_diff(previous, current))
+}
+
+/// One step of a [`diff`]: indices into the old and new sequences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffOp {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// LCS diff of two sequences, in order.
+pub fn diff<T: PartialEq>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    // Trim the shared prefix and suffix before building the LCS table.
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (n, m) = (old.len() - prefix - suffix, new.len() - prefix - suffix);
+
+    let mut ops: Vec<DiffOp> = (0..prefix).map(|i| DiffOp::Equal(i, i)).collect();
+    if n.saturating_mul(m) > MAX_DIFF_CELLS {
+        ops.extend((prefix..prefix + n).map(DiffOp::Delete));
+        ops.extend((prefix..prefix + m).map(DiffOp::Insert));
+    } else {
+        let old_mid = &old[prefix..prefix + n];
+        let new_mid = &new[prefix..prefix + m];
+        // lcs[i][j] = LCS length of old_mid[i..] and new_mid[j..].
+        let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if old_mid[i] == new_mid[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            if i < n && j < m && old_mid[i] == new_mid[j] {
+                ops.push(DiffOp::Equal(prefix + i, prefix + j));
+                i += 1;
+                j += 1;
+            } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+                ops.push(DiffOp::Delete(prefix + i));
+                i += 1;
+            } else {
+                ops.push(DiffOp::Insert(prefix + j));
+                j += 1;
+            }
+        }
+    }
+    let (old_tail, new_tail) = (old.len() - suffix, new.len() - suffix);
+    ops.extend((0..suffix).map(|k| DiffOp::Equal(old_tail + k, new_tail + k)));
+    ops
+}
+
+/// Line diff of `old` to `new`: unchanged lines start with a space, removed
+/// lines with `-`, added lines with `+`.
+pub fn line_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let mut out = String::new();
+    for op in diff(&old, &new) {
+        let (marker, line) = match op {
+            DiffOp::Equal(i, _) => (' ', old[i]),
+            DiffOp::Delete(i) => ('-', old[i]),
+            DiffOp::Insert(j) => ('+', new[j]),
+        };
+        out.push(marker);
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_marks_added_and_removed_lines() {
+        let diff = line_diff("a\nb\nc\n", "a\nc\nd\n");
+        assert_eq!(diff, " a\n-b\n c\n+d\n");
+        assert_eq!(line_diff("", "new"), "+new\n");
+        assert_eq!(line_diff("same", "same"), " same\n");
+        assert_eq!(stored_revision_diff("a", "b").as_deref(), Some("-a\n+b\n"));
+    }
+
+    #[test]
+    fn etags_match_quoted_lists_and_wildcards() {
+        let etag = content_hash("draft");
+        assert!(etag_matches(&format!("\"{etag}\""), "draft"));
+        assert!(etag_matches(&format!("\"stale\", W/\"{etag}\""), "draft"));
+        assert!(etag_matches("*", "draft"));
+        assert!(!etag_matches(&etag, "edited draft"));
+    }
+
+    #[test]
+    fn hashes_are_stable_hex() {
+        assert_eq!(
+            content_hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -1,7 +1,8 @@
 //! Memory hygiene: automatic cleanup of stale workspace documents.
 //!
 //! Runs on a configurable cadence and deletes daily log entries older
-//! than the retention period. Identity files (`IDENTITY.md`, `SOUL.md`,
+//! than the retention period, then purges trash entries older than the
+//! trash retention period. Identity files (`IDENTITY.md`, `SOUL.md`,
 //! etc.) are never touched.
 //!
 //! ```text
@@ -11,7 +12,8 @@
 //! │  1. Check cadence (skip if ran recently)     │
 //! │  2. List daily/ documents                    │
 //! │  3. Delete those older than retention_days   │
-//! │  4. Log summary                              │
+//! │  4. Purge trash past trash_retention_days    │
+//! │  5. Log summary                              │
 //! └─────────────────────────────────────────────┘
 //! ```
 
@@ -29,6 +31,8 @@ pub struct HygieneConfig {
     pub enabled: bool,
     /// Documents in `daily/` older than this many days are deleted.
     pub retention_days: u32,
+    /// Trash entries older than this many days are purged.
+    pub trash_retention_days: u32,
     /// Minimum hours between hygiene passes.
     pub cadence_hours: u32,
     /// Directory to store state file (default: `~/.clawyer`).
@@ -44,6 +48,7 @@ impl Default for HygieneConfig {
         Self {
             enabled: true,
             retention_days: 30,
+            trash_retention_days: 30,
             cadence_hours: 12,
             state_dir,
         }
@@ -61,6 +66,8 @@ struct HygieneState {
 pub struct HygieneReport {
     /// Number of daily log documents deleted.
     pub daily_logs_deleted: u32,
+    /// Number of trash entries purged past their retention.
+    pub trash_purged: u32,
     /// Whether the run was skipped (cadence not yet elapsed).
     pub skipped: bool,
 }
@@ -68,7 +75,7 @@ pub struct HygieneReport {
 impl HygieneReport {
     /// True if any cleanup work was done.
     pub fn had_work(&self) -> bool {
-        self.daily_logs_deleted > 0
+        self.daily_logs_deleted > 0 || self.trash_purged > 0
     }
 }
 
@@ -116,9 +123,16 @@ pub async fn run_if_due(workspace: &Workspace, config: &HygieneConfig) -> Hygien
         Err(e) => tracing::warn!("memory hygiene: failed to clean daily logs: {e}"),
     }
 
+    // Purge expired trash
+    match purge_expired_trash(workspace, config.trash_retention_days).await {
+        Ok(count) => report.trash_purged = count,
+        Err(e) => tracing::warn!("memory hygiene: failed to purge trash: {e}"),
+    }
+
     if report.had_work() {
         tracing::info!(
             daily_logs_deleted = report.daily_logs_deleted,
+            trash_purged = report.trash_purged,
             "memory hygiene: cleanup complete"
         );
     } else {
@@ -167,6 +181,25 @@ async fn cleanup_daily_logs(
     Ok(deleted)
 }
 
+/// Permanently delete trash entries older than `retention_days`.
+async fn purge_expired_trash(
+    workspace: &Workspace,
+    retention_days: u32,
+) -> Result<u32, anyhow::Error> {
+    let cutoff = Utc::now() - chrono::Duration::days(i64::from(retention_days));
+    let purged = workspace.purge_trash_before(cutoff).await?;
+    if !purged.is_empty() {
+        crate::legal::audit::record(
+            "memory_trash_expired",
+            serde_json::json!({
+                "retention_days": retention_days,
+                "paths": purged.iter().map(|doc| doc.path.as_str()).collect::<Vec<_>>(),
+            }),
+        );
+    }
+    Ok(purged.len() as u32)
+}
+
 fn state_path_dir(state_file: &std::path::Path) -> Option<&std::path::Path> {
     state_file.parent()
 }
@@ -199,6 +232,7 @@ mod tests {
         let cfg = HygieneConfig::default();
         assert!(cfg.enabled);
         assert_eq!(cfg.retention_days, 30);
+        assert_eq!(cfg.trash_retention_days, 30);
         assert_eq!(cfg.cadence_hours, 12);
     }
 
@@ -213,7 +247,7 @@ mod tests {
     fn report_had_work_when_deleted() {
         let report = HygieneReport {
             daily_logs_deleted: 3,
-            skipped: false,
+            ..Default::default()
         };
         assert!(report.had_work());
     }
@@ -0,0 +1,276 @@
+//! Local ONNX sentence-transformer embeddings.
+//!
+//! Runs an exported sentence-transformers model (e.g. `all-MiniLM-L6-v2`)
+//! in-process through ONNX Runtime so document text never leaves the host.
+//! The model directory must contain `model.onnx` (or `onnx/model.onnx`) and
+//! the Hugging Face `tokenizer.json`. ONNX Runtime is loaded dynamically;
+//! set `ORT_DYLIB_PATH` to `libonnxruntime` if it is not on the loader path.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use ort::session::Session;
+use ort::value::Tensor;
+use tokenizers::{PaddingParams, Tokenizer, TruncationParams};
+
+use super::embeddings::{EmbeddingError, EmbeddingProvider};
+
+/// Sentence-transformer models are trained on at most 512 tokens.
+const MAX_TOKENS: usize = 512;
+
+fn local_error(message: impl Into<String>) -> EmbeddingError {
+    EmbeddingError::LocalModel(message.into())
+}
+
+/// Embedding provider backed by a local ONNX model.
+pub struct LocalOnnxEmbeddings {
+    session: Arc<Mutex<Session>>,
+    tokenizer: Arc<Tokenizer>,
+    model: String,
+    dimension: usize,
+    /// BERT-style exports take `token_type_ids`; others (e.g. MPNet) do not.
+    uses_token_type_ids: bool,
+}
+
+impl LocalOnnxEmbeddings {
+    /// Load the model and tokenizer from `dir`.
+    ///
+    /// `dimension` must match the model's hidden size; it is checked on
+    /// every batch.
+    pub fn from_dir(dir: impl AsRef<Path>, dimension: usize) -> Result<Self, EmbeddingError> {
+        let dir = dir.as_ref();
+        let model_path = [dir.join("model.onnx"), dir.join("onnx").join("model.onnx")]
+            .into_iter()
+            .find(|path| path.is_file())
+            .ok_or_else(|| local_error(format!("no model.onnx found in {}", dir.display())))?;
+
+        let mut tokenizer = Tokenizer::from_file(dir.join("tokenizer.json"))
+            .map_err(|e| local_error(format!("failed to load tokenizer.json: {e}")))?;
+        tokenizer.with_padding(Some(PaddingParams::default()));
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: MAX_TOKENS,
+                ..Default::default()
+            }))
+            .map_err(|e| local_error(format!("failed to configure truncation: {e}")))?;
+
+        let session = Session::builder()
+            .and_then(|builder| builder.commit_from_file(&model_path))
+            .map_err(|e| local_error(format!("failed to load {}: {e}", model_path.display())))?;
+        let uses_token_type_ids = session
+            .inputs
+            .iter()
+            .any(|input| input.name == "token_type_ids");
+
+        let model = dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("local-onnx")
+            .to_string();
+
+        Ok(Self {
+            session: Arc::new(Mutex::new(session)),
+            tokenizer: Arc::new(tokenizer),
+            model,
+            dimension,
+            uses_token_type_ids,
+        })
+    }
+
+    /// Override the reported model name (defaults to the directory name).
+    pub fn with_model_name(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    fn run(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        let encodings = self
+            .tokenizer
+            .encode_batch(texts, true)
+            .map_err(|e| local_error(format!("tokenization failed: {e}")))?;
+        let batch = encodings.len();
+        let seq_len = encodings.first().map_or(0, |encoding| encoding.len());
+
+        let mut input_ids = Vec::with_capacity(batch * seq_len);
+        let mut attention_mask = Vec::with_capacity(batch * seq_len);
+        let mut token_type_ids = Vec::with_capacity(batch * seq_len);
+        for encoding in &encodings {
+            input_ids.extend(encoding.get_ids().iter().map(|&id| i64::from(id)));
+            attention_mask.extend(encoding.get_attention_mask().iter().map(|&m| i64::from(m)));
+            token_type_ids.extend(encoding.get_type_ids().iter().map(|&t| i64::from(t)));
+        }
+
+        let shape = [batch as i64, seq_len as i64];
+        let tensor = |data: Vec<i64>| {
+            Tensor::from_array((shape, data))
+                .map_err(|e| local_error(format!("failed to build input tensor: {e}")))
+        };
+        let mut inputs = ort::inputs![
+            "input_ids" => tensor(input_ids)?,
+            "attention_mask" => tensor(attention_mask.clone())?,
+        ];
+        if self.uses_token_type_ids {
+            inputs.push(("token_type_ids".into(), tensor(token_type_ids)?.into()));
+        }
+
+        let mut session = self
+            .session
+            .lock()
+            .map_err(|_| local_error("ONNX session lock poisoned"))?;
+        let outputs = session
+            .run(inputs)
+            .map_err(|e| local_error(format!("inference failed: {e}")))?;
+        let (out_shape, data) = outputs[0]
+            .try_extract_tensor::<f32>()
+            .map_err(|e| local_error(format!("unexpected model output: {e}")))?;
+
+        let embeddings = match **out_shape {
+            // [batch, seq, hidden]: token embeddings, mean-pool them.
+            [b, s, hidden] if b as usize == batch && s as usize == seq_len => {
+                mean_pool(data, &attention_mask, batch, seq_len, hidden as usize)
+            }
+            // [batch, hidden]: the export already pools.
+            [b, hidden] if b as usize == batch => data
+                .chunks(hidden as usize)
+                .map(|row| row.to_vec())
+                .collect(),
+            ref other => {
+                return Err(local_error(format!(
+                    "unsupported model output shape {other:?}"
+                )));
+            }
+        };
+
+        embeddings
+            .into_iter()
+            .map(|mut embedding| {
+                if embedding.len() != self.dimension {
+                    return Err(EmbeddingError::InvalidResponse(format!(
+                        "local model returned embedding of dimension {}, expected {}",
+                        embedding.len(),
+                        self.dimension
+                    )));
+                }
+                l2_normalize(&mut embedding);
+                Ok(embedding)
+            })
+            .collect()
+    }
+}
+
+/// Average token embeddings over non-padding positions.
+fn mean_pool(
+    data: &[f32],
+    attention_mask: &[i64],
+    batch: usize,
+    seq_len: usize,
+    hidden: usize,
+) -> Vec<Vec<f32>> {
+    (0..batch)
+        .map(|b| {
+            let mut pooled = vec![0.0f32; hidden];
+            let mut count = 0.0f32;
+            for t in 0..seq_len {
+                if attention_mask[b * seq_len + t] == 0 {
+                    continue;
+                }
+                count += 1.0;
+                let offset = (b * seq_len + t) * hidden;
+                for (acc, value) in pooled.iter_mut().zip(&data[offset..offset + hidden]) {
+                    *acc += value;
+                }
+            }
+            if count > 0.0 {
+                for value in &mut pooled {
+                    *value /= count;
+                }
+            }
+            pooled
+        })
+        .collect()
+}
+
+fn l2_normalize(embedding: &mut [f32]) {
+    let magnitude: f32 = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if magnitude > 0.0 {
+        for x in embedding {
+            *x /= magnitude;
+        }
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for LocalOnnxEmbeddings {
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+
+    fn max_input_length(&self) -> usize {
+        // Inputs are truncated to MAX_TOKENS; ~4 chars per token.
+        MAX_TOKENS * 4
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>, EmbeddingError> {
+        let embeddings = self.embed_batch(&[text.to_string()]).await?;
+        embeddings
+            .into_iter()
+            .next()
+            .ok_or_else(|| EmbeddingError::InvalidResponse("No embedding returned".to_string()))
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let provider = Self {
+            session: Arc::clone(&self.session),
+            tokenizer: Arc::clone(&self.tokenizer),
+            model: self.model.clone(),
+            dimension: self.dimension,
+            uses_token_type_ids: self.uses_token_type_ids,
+        };
+        let texts = texts.to_vec();
+        tokio::task::spawn_blocking(move || provider.run(texts))
+            .await
+            .map_err(|e| local_error(format!("embedding task failed: {e}")))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mean_pool_ignores_padding() {
+        // Two sequences of length 2, hidden size 2; the second is padded.
+        let data = [1.0, 3.0, 3.0, 5.0, 2.0, 2.0, 100.0, 100.0];
+        let mask = [1, 1, 1, 0];
+        let pooled = mean_pool(&data, &mask, 2, 2, 2);
+        assert_eq!(pooled, vec![vec![2.0, 4.0], vec![2.0, 2.0]]);
+    }
+
+    #[test]
+    fn l2_normalize_produces_unit_vectors() {
+        let mut embedding = vec![3.0, 4.0];
+        l2_normalize(&mut embedding);
+        assert_eq!(embedding, vec![0.6, 0.8]);
+
+        let mut zeros = vec![0.0, 0.0];
+        l2_normalize(&mut zeros);
+        assert_eq!(zeros, vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn from_dir_reports_missing_model() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let err = LocalOnnxEmbeddings::from_dir(dir.path(), 384)
+            .err()
+            .expect("missing model should fail");
+        assert!(err.to_string().contains("model.onnx"));
+    }
+}
@@ -40,26 +40,40 @@
 //! 3. **Self-documenting**: Use README.md files to describe directory structure
 //! 4. **Hybrid search**: Vector similarity + BM25 full-text via RRF
 
+pub mod backfill;
 mod chunker;
 mod document;
 mod embeddings;
+pub mod git_mirror;
+pub mod history;
 pub mod hygiene;
+#[cfg(feature = "local-embeddings")]
+mod local_embeddings;
 #[cfg(feature = "postgres")]
 mod repository;
 mod search;
+pub mod tags;
 
-pub use chunker::{ChunkConfig, chunk_document};
-pub use document::{MemoryChunk, MemoryDocument, WorkspaceEntry, paths};
+pub use chunker::{ChunkConfig, chunk_document, locate_chunk};
+pub use document::{
+    CitedChunk, DocumentRevision, MemoryChunk, MemoryDocument, TrashedDocument, WorkspaceEntry,
+    WorkspaceStorageUsage, paths,
+};
 pub use embeddings::{
     EmbeddingProvider, MockEmbeddings, NearAiEmbeddings, OllamaEmbeddings, OpenAiEmbeddings,
 };
+#[cfg(feature = "local-embeddings")]
+pub use local_embeddings::LocalOnnxEmbeddings;
 #[cfg(feature = "postgres")]
 pub use repository::Repository;
-pub use search::{RankedResult, SearchConfig, SearchResult, reciprocal_rank_fusion};
+pub use search::{
+    RankedResult, SearchConfig, SearchResult, chunk_anchor, extract_chunk_anchors,
+    reciprocal_rank_fusion,
+};
 
 use std::sync::Arc;
 
-use chrono::{NaiveDate, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 #[cfg(feature = "postgres")]
 use deadpool_postgres::Pool;
 use uuid::Uuid;
@@ -119,11 +133,16 @@ impl WorkspaceStorage {
         }
     }
 
-    async fn update_document(&self, id: Uuid, content: &str) -> Result<(), WorkspaceError> {
+    async fn update_document(
+        &self,
+        id: Uuid,
+        content: &str,
+        if_match: Option<&str>,
+    ) -> Result<(), WorkspaceError> {
         match self {
             #[cfg(feature = "postgres")]
-            Self::Repo(repo) => repo.update_document(id, content).await,
-            Self::Db(db) => db.update_document(id, content).await,
+            Self::Repo(repo) => repo.update_document(id, content, if_match).await,
+            Self::Db(db) => db.update_document(id, content, if_match).await,
         }
     }
 
@@ -140,6 +159,103 @@ impl WorkspaceStorage {
         }
     }
 
+    async fn trash_document_by_path(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        path: &str,
+        deleted_by: &str,
+    ) -> Result<TrashedDocument, WorkspaceError> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Repo(repo) => {
+                repo.trash_document_by_path(user_id, agent_id, path, deleted_by)
+                    .await
+            }
+            Self::Db(db) => {
+                db.trash_document_by_path(user_id, agent_id, path, deleted_by)
+                    .await
+            }
+        }
+    }
+
+    async fn list_trashed_documents(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+    ) -> Result<Vec<TrashedDocument>, WorkspaceError> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Repo(repo) => repo.list_trashed_documents(user_id, agent_id).await,
+            Self::Db(db) => db.list_trashed_documents(user_id, agent_id).await,
+        }
+    }
+
+    async fn get_trashed_document(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        id: Uuid,
+    ) -> Result<Option<TrashedDocument>, WorkspaceError> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Repo(repo) => repo.get_trashed_document(user_id, agent_id, id).await,
+            Self::Db(db) => db.get_trashed_document(user_id, agent_id, id).await,
+        }
+    }
+
+    async fn delete_trashed_document(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        id: Uuid,
+    ) -> Result<bool, WorkspaceError> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Repo(repo) => repo.delete_trashed_document(user_id, agent_id, id).await,
+            Self::Db(db) => db.delete_trashed_document(user_id, agent_id, id).await,
+        }
+    }
+
+    async fn purge_trashed_documents(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<TrashedDocument>, WorkspaceError> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Repo(repo) => {
+                repo.purge_trashed_documents(user_id, agent_id, cutoff)
+                    .await
+            }
+            Self::Db(db) => db.purge_trashed_documents(user_id, agent_id, cutoff).await,
+        }
+    }
+
+    async fn list_document_revisions(
+        &self,
+        document_id: Uuid,
+    ) -> Result<Vec<DocumentRevision>, WorkspaceError> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Repo(repo) => repo.list_document_revisions(document_id).await,
+            Self::Db(db) => db.list_document_revisions(document_id).await,
+        }
+    }
+
+    async fn get_document_revision(
+        &self,
+        document_id: Uuid,
+        revision: i64,
+    ) -> Result<Option<DocumentRevision>, WorkspaceError> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Repo(repo) => repo.get_document_revision(document_id, revision).await,
+            Self::Db(db) => db.get_document_revision(document_id, revision).await,
+        }
+    }
+
     async fn list_directory(
         &self,
         user_id: &str,
@@ -224,6 +340,32 @@ impl WorkspaceStorage {
         }
     }
 
+    async fn get_chunk(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        chunk_id: Uuid,
+    ) -> Result<Option<CitedChunk>, WorkspaceError> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Repo(repo) => repo.get_chunk(user_id, agent_id, chunk_id).await,
+            Self::Db(db) => db.get_chunk(user_id, agent_id, chunk_id).await,
+        }
+    }
+
+    async fn storage_usage(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        path_prefix: Option<&str>,
+    ) -> Result<WorkspaceStorageUsage, WorkspaceError> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Repo(repo) => repo.storage_usage(user_id, agent_id, path_prefix).await,
+            Self::Db(db) => db.storage_usage(user_id, agent_id, path_prefix).await,
+        }
+    }
+
     async fn hybrid_search(
         &self,
         user_id: &str,
@@ -353,6 +495,18 @@ impl Workspace {
         self
     }
 
+    /// Whether an embedding provider is configured.
+    pub fn has_embeddings(&self) -> bool {
+        self.embeddings.is_some()
+    }
+
+    /// Model name of the configured embedding provider.
+    pub fn embedding_model(&self) -> Option<&str> {
+        self.embeddings
+            .as_ref()
+            .map(|provider| provider.model_name())
+    }
+
     /// Configure matter-scoped encryption/decryption for legal workspace files.
     pub fn with_legal_content_policy(mut self, policy: LegalContentPolicy) -> Self {
         self.legal_content_policy = Some(policy);
@@ -381,12 +535,17 @@ impl Workspace {
     /// println!("{}", doc.content);
     /// ```
     pub async fn read(&self, path: &str) -> Result<MemoryDocument, WorkspaceError> {
-        let path = normalize_path(path);
-        let mut doc = self
-            .storage
-            .get_document_by_path(&self.user_id, self.agent_id, &path)
-            .await?;
+        self.read_with_etag(path).await.map(|(doc, _)| doc)
+    }
 
+    /// Read a file along with its ETag, the hash of its stored content.
+    pub async fn read_with_etag(
+        &self,
+        path: &str,
+    ) -> Result<(MemoryDocument, String), WorkspaceError> {
+        let path = normalize_path(path);
+        let mut doc = self.read_stored(&path).await?;
+        let etag = history::content_hash(&doc.content);
         if let Some(policy) = self.legal_content_policy.as_ref()
             && let Some(matter_id) = policy.matter_id_for_path(&path)
             && let Some(plaintext) = crate::legal::workspace_crypto::decrypt_matter_content(
@@ -398,8 +557,7 @@ impl Workspace {
         {
             doc.content = plaintext;
         }
-
-        Ok(doc)
+        Ok((doc, etag))
     }
 
     /// Read the stored content without legal decryption transforms.
@@ -420,6 +578,23 @@ impl Workspace {
     /// workspace.write("projects/alpha/README.md", "# Project Alpha\n\nDescription here.").await?;
     /// ```
     pub async fn write(&self, path: &str, content: &str) -> Result<MemoryDocument, WorkspaceError> {
+        self.write_if_match(path, content, None)
+            .await
+            .map(|(doc, _)| doc)
+    }
+
+    /// Write a file only if it is unchanged since the caller read it.
+    ///
+    /// `if_match` is an ETag from [`Workspace::read_with_etag`]; a document
+    /// that changed (or no longer exists) fails with `WriteConflict`. With
+    /// `None` this is a plain [`Workspace::write`]. Returns the document and
+    /// its new ETag.
+    pub async fn write_if_match(
+        &self,
+        path: &str,
+        content: &str,
+        if_match: Option<&str>,
+    ) -> Result<(MemoryDocument, String), WorkspaceError> {
         let path = normalize_path(path);
         let mut stored_content = content.to_string();
         let mut skip_index = false;
@@ -435,12 +610,26 @@ impl Workspace {
             skip_index = policy.exclude_from_search;
         }
 
-        let doc = self
-            .storage
-            .get_or_create_document_by_path(&self.user_id, self.agent_id, &path)
-            .await?;
+        let doc = match if_match {
+            // Conditional writes never create the document.
+            Some(_) => self
+                .storage
+                .get_document_by_path(&self.user_id, self.agent_id, &path)
+                .await
+                .map_err(|e| match e {
+                    WorkspaceError::DocumentNotFound { .. } => {
+                        WorkspaceError::WriteConflict { current_etag: None }
+                    }
+                    other => other,
+                })?,
+            None => {
+                self.storage
+                    .get_or_create_document_by_path(&self.user_id, self.agent_id, &path)
+                    .await?
+            }
+        };
         self.storage
-            .update_document(doc.id, &stored_content)
+            .update_document(doc.id, &stored_content, if_match)
             .await?;
 
         if skip_index {
@@ -449,7 +638,8 @@ impl Workspace {
             self.reindex_document(doc.id).await?;
         }
 
-        self.read(&path).await
+        let etag = history::content_hash(&stored_content);
+        Ok((self.read(&path).await?, etag))
     }
 
     /// Write stored content directly without additional encryption transforms.
@@ -465,7 +655,7 @@ impl Workspace {
             .storage
             .get_or_create_document_by_path(&self.user_id, self.agent_id, &path)
             .await?;
-        self.storage.update_document(doc.id, content).await?;
+        self.storage.update_document(doc.id, content, None).await?;
 
         let skip_index = self
             .legal_content_policy
@@ -504,7 +694,9 @@ impl Workspace {
             format!("{}\n{}", doc.content, content)
         };
 
-        self.storage.update_document(doc.id, &new_content).await?;
+        self.storage
+            .update_document(doc.id, &new_content, None)
+            .await?;
         self.reindex_document(doc.id).await?;
         Ok(())
     }
@@ -533,6 +725,142 @@ impl Workspace {
             .await
     }
 
+    /// Move a file to the trash instead of deleting it.
+    ///
+    /// The stored content (still encrypted for matter files) is kept until
+    /// the entry is restored or purged; search chunks are dropped.
+    pub async fn trash(
+        &self,
+        path: &str,
+        deleted_by: &str,
+    ) -> Result<TrashedDocument, WorkspaceError> {
+        let path = normalize_path(path);
+        self.storage
+            .trash_document_by_path(&self.user_id, self.agent_id, &path, deleted_by)
+            .await
+    }
+
+    /// Trash entries, most recently deleted first.
+    pub async fn list_trash(&self) -> Result<Vec<TrashedDocument>, WorkspaceError> {
+        self.storage
+            .list_trashed_documents(&self.user_id, self.agent_id)
+            .await
+    }
+
+    /// Look up one trash entry.
+    pub async fn get_trashed(&self, id: Uuid) -> Result<Option<TrashedDocument>, WorkspaceError> {
+        self.storage
+            .get_trashed_document(&self.user_id, self.agent_id, id)
+            .await
+    }
+
+    /// Put a trashed file back at its original path and re-index it.
+    ///
+    /// Refuses to overwrite a document written to that path since deletion.
+    pub async fn restore_from_trash(&self, id: Uuid) -> Result<MemoryDocument, WorkspaceError> {
+        let entry =
+            self.get_trashed(id)
+                .await?
+                .ok_or_else(|| WorkspaceError::DocumentNotFound {
+                    doc_type: format!("trash entry {id}"),
+                    user_id: self.user_id.clone(),
+                })?;
+        if self.exists(&entry.path).await? {
+            return Err(WorkspaceError::AlreadyExists { path: entry.path });
+        }
+        let doc = self.write_stored(&entry.path, &entry.content).await?;
+        self.storage
+            .delete_trashed_document(&self.user_id, self.agent_id, id)
+            .await?;
+        Ok(doc)
+    }
+
+    /// Permanently delete one trash entry; `false` when it was not there.
+    pub async fn purge_from_trash(&self, id: Uuid) -> Result<bool, WorkspaceError> {
+        self.storage
+            .delete_trashed_document(&self.user_id, self.agent_id, id)
+            .await
+    }
+
+    /// Permanently delete trash entries deleted before `cutoff`.
+    pub async fn purge_trash_before(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<TrashedDocument>, WorkspaceError> {
+        self.storage
+            .purge_trashed_documents(&self.user_id, self.agent_id, cutoff)
+            .await
+    }
+
+    /// Content revisions of a file, newest first.
+    ///
+    /// Encrypted matter revisions are decrypted and their diffs computed
+    /// here, since storage only keeps diffs of plaintext.
+    pub async fn history(&self, path: &str) -> Result<Vec<DocumentRevision>, WorkspaceError> {
+        let path = normalize_path(path);
+        let doc = self
+            .storage
+            .get_document_by_path(&self.user_id, self.agent_id, &path)
+            .await?;
+        let mut revisions = self.storage.list_document_revisions(doc.id).await?;
+        let Some(policy) = self.legal_content_policy.as_ref() else {
+            return Ok(revisions);
+        };
+        let Some(matter_id) = policy.matter_id_for_path(&path) else {
+            return Ok(revisions);
+        };
+        let mut needs_diff = Vec::with_capacity(revisions.len());
+        for revision in &mut revisions {
+            needs_diff.push(revision.diff.is_none());
+            if let Some(plaintext) = crate::legal::workspace_crypto::decrypt_matter_content(
+                policy.crypto.as_ref(),
+                &matter_id,
+                &revision.content,
+            )
+            .map_err(|e| WorkspaceError::SearchFailed { reason: e })?
+            {
+                revision.content = plaintext;
+            }
+        }
+        // Newest first, so each revision's predecessor is the next entry.
+        for i in 0..revisions.len() {
+            if needs_diff[i] {
+                let previous = revisions
+                    .get(i + 1)
+                    .map(|r| r.content.as_str())
+                    .unwrap_or("");
+                revisions[i].diff = Some(history::line_diff(previous, &revisions[i].content));
+            }
+        }
+        Ok(revisions)
+    }
+
+    /// Write an earlier revision back as the file's current content.
+    ///
+    /// The restore is itself recorded as a new revision, so nothing in the
+    /// history is lost.
+    pub async fn restore_revision(
+        &self,
+        path: &str,
+        revision: i64,
+    ) -> Result<MemoryDocument, WorkspaceError> {
+        let path = normalize_path(path);
+        let doc = self
+            .storage
+            .get_document_by_path(&self.user_id, self.agent_id, &path)
+            .await?;
+        let stored = self
+            .storage
+            .get_document_revision(doc.id, revision)
+            .await?
+            .ok_or_else(|| WorkspaceError::DocumentNotFound {
+                doc_type: format!("{path} revision {revision}"),
+                user_id: self.user_id.clone(),
+            })?;
+        self.write_stored(&path, &stored.content).await?;
+        self.read(&path).await
+    }
+
     /// List files and directories in a path.
     ///
     /// Returns immediate children (not recursive).
@@ -563,6 +891,19 @@ impl Workspace {
             .await
     }
 
+    /// Storage footprint of documents under a directory (whole workspace
+    /// when `directory` is empty).
+    pub async fn storage_usage(
+        &self,
+        directory: &str,
+    ) -> Result<WorkspaceStorageUsage, WorkspaceError> {
+        let directory = normalize_directory(directory);
+        let prefix = (!directory.is_empty()).then(|| format!("{directory}/"));
+        self.storage
+            .storage_usage(&self.user_id, self.agent_id, prefix.as_deref())
+            .await
+    }
+
     // ==================== Convenience Methods ====================
 
     /// Get the main MEMORY.md document (long-term curated memory).
@@ -623,7 +964,9 @@ impl Workspace {
         } else {
             format!("{}\n\n{}", doc.content, entry)
         };
-        self.storage.update_document(doc.id, &new_content).await?;
+        self.storage
+            .update_document(doc.id, &new_content, None)
+            .await?;
         self.reindex_document(doc.id).await?;
         Ok(())
     }
@@ -751,6 +1094,16 @@ impl Workspace {
             .await
     }
 
+    /// Resolve a cited chunk in this workspace.
+    ///
+    /// Returns `None` for chunks that belong to another user or agent, or
+    /// that were replaced when their document was re-indexed.
+    pub async fn get_chunk(&self, chunk_id: Uuid) -> Result<Option<CitedChunk>, WorkspaceError> {
+        self.storage
+            .get_chunk(&self.user_id, self.agent_id, chunk_id)
+            .await
+    }
+
     // ==================== Indexing ====================
 
     /// Re-index a document (chunk and generate embeddings).
@@ -934,6 +1287,35 @@ impl Workspace {
 
         Ok(count)
     }
+
+    /// Fetch up to `limit` chunks that still lack an embedding.
+    pub async fn chunks_without_embeddings(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<MemoryChunk>, WorkspaceError> {
+        self.storage
+            .get_chunks_without_embeddings(&self.user_id, self.agent_id, limit)
+            .await
+    }
+
+    /// Generate and store the embedding for a single chunk.
+    pub async fn embed_chunk(&self, chunk_id: Uuid, content: &str) -> Result<(), WorkspaceError> {
+        let Some(ref provider) = self.embeddings else {
+            return Err(WorkspaceError::EmbeddingFailed {
+                reason: "no embedding provider configured".to_string(),
+            });
+        };
+        let embedding =
+            provider
+                .embed(content)
+                .await
+                .map_err(|e| WorkspaceError::EmbeddingFailed {
+                    reason: e.to_string(),
+                })?;
+        self.storage
+            .update_chunk_embedding(chunk_id, &embedding)
+            .await
+    }
 }
 
 /// Normalize a file path (remove leading/trailing slashes, collapse //).
@@ -9,26 +9,46 @@ use deadpool_postgres::Pool;
 use pgvector::Vector;
 use uuid::Uuid;
 
+use crate::db::postgres::failover::PgPools;
 use crate::error::WorkspaceError;
 
-use crate::workspace::document::{MemoryChunk, MemoryDocument, WorkspaceEntry};
+use crate::workspace::document::{
+    CitedChunk, DocumentRevision, MemoryChunk, MemoryDocument, TrashedDocument, WorkspaceEntry,
+    WorkspaceStorageUsage,
+};
+use crate::workspace::history;
 use crate::workspace::search::{RankedResult, SearchConfig, SearchResult, reciprocal_rank_fusion};
 
 /// Database repository for workspace operations.
 pub struct Repository {
-    pool: Pool,
+    pools: PgPools,
 }
 
 impl Repository {
     /// Create a new repository with a connection pool.
     pub fn new(pool: Pool) -> Self {
-        Self { pool }
+        Self::with_pools(PgPools::from_pool(pool))
     }
 
-    /// Get a connection from the pool.
+    /// Create a repository that shares the backend's primary and replica pools.
+    pub fn with_pools(pools: PgPools) -> Self {
+        Self { pools }
+    }
+
+    /// Get a connection from the primary pool.
     async fn conn(&self) -> Result<deadpool_postgres::Object, WorkspaceError> {
-        self.pool
-            .get()
+        self.pools
+            .conn()
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Failed to get connection: {}", e),
+            })
+    }
+
+    /// Get a connection for search and listings, preferring the read replica.
+    async fn read_conn(&self) -> Result<deadpool_postgres::Object, WorkspaceError> {
+        self.pools
+            .read_conn()
             .await
             .map_err(|e| WorkspaceError::SearchFailed {
                 reason: format!("Failed to get connection: {}", e),
@@ -134,22 +154,139 @@ impl Repository {
         self.get_document_by_path(user_id, agent_id, path).await
     }
 
-    /// Update a document's content.
-    pub async fn update_document(&self, id: Uuid, content: &str) -> Result<(), WorkspaceError> {
-        let conn = self.conn().await?;
+    /// Update a document's content, recording a revision when it changes.
+    ///
+    /// A document written before history existed gets its prior content
+    /// recorded as a baseline revision first. With `if_match`, the write is
+    /// rejected unless the stored content still has that ETag.
+    pub async fn update_document(
+        &self,
+        id: Uuid,
+        content: &str,
+        if_match: Option<&str>,
+    ) -> Result<(), WorkspaceError> {
+        let update_err = |e: tokio_postgres::Error| WorkspaceError::SearchFailed {
+            reason: format!("Update failed: {}", e),
+        };
+        let mut conn = self.conn().await?;
+        let tx = conn.transaction().await.map_err(update_err)?;
 
-        conn.execute(
+        let previous: Option<String> = tx
+            .query_opt(
+                "SELECT content FROM memory_documents WHERE id = $1 FOR UPDATE",
+                &[&id],
+            )
+            .await
+            .map_err(update_err)?
+            .map(|row| row.get("content"));
+        if let Some(if_match) = if_match {
+            match previous.as_deref() {
+                Some(previous) if history::etag_matches(if_match, previous) => {}
+                previous => {
+                    return Err(WorkspaceError::WriteConflict {
+                        current_etag: previous.map(history::content_hash),
+                    });
+                }
+            }
+        }
+        tx.execute(
             "UPDATE memory_documents SET content = $2, updated_at = NOW() WHERE id = $1",
             &[&id, &content],
         )
         .await
-        .map_err(|e| WorkspaceError::SearchFailed {
-            reason: format!("Update failed: {}", e),
-        })?;
+        .map_err(update_err)?;
+
+        if let Some(previous) = previous.filter(|previous| previous != content) {
+            let latest: i64 = tx
+                .query_one(
+                    "SELECT COALESCE(MAX(revision), 0)::BIGINT AS latest \
+                     FROM memory_document_revisions WHERE document_id = $1",
+                    &[&id],
+                )
+                .await
+                .map_err(update_err)?
+                .get("latest");
+            let mut revisions = Vec::new();
+            if latest == 0 && !previous.is_empty() {
+                revisions.push((previous.clone(), None));
+            }
+            revisions.push((
+                content.to_string(),
+                history::stored_revision_diff(&previous, content),
+            ));
+            for (offset, (revision_content, diff)) in revisions.iter().enumerate() {
+                let revision = latest + offset as i64 + 1;
+                tx.execute(
+                    "INSERT INTO memory_document_revisions \
+                     (document_id, revision, content, content_hash, diff) \
+                     VALUES ($1, $2, $3, $4, $5)",
+                    &[
+                        &id,
+                        &(revision as i32),
+                        revision_content,
+                        &history::content_hash(revision_content),
+                        diff,
+                    ],
+                )
+                .await
+                .map_err(update_err)?;
+            }
+        }
 
+        tx.commit().await.map_err(update_err)?;
         Ok(())
     }
 
+    /// Content revisions of a document, newest first.
+    pub async fn list_document_revisions(
+        &self,
+        document_id: Uuid,
+    ) -> Result<Vec<DocumentRevision>, WorkspaceError> {
+        let conn = self.conn().await?;
+        let rows = conn
+            .query(
+                "SELECT id, document_id, revision, content, content_hash, diff, created_at \
+                 FROM memory_document_revisions WHERE document_id = $1 ORDER BY revision DESC",
+                &[&document_id],
+            )
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Revision query failed: {}", e),
+            })?;
+        Ok(rows.iter().map(Self::row_to_document_revision).collect())
+    }
+
+    pub async fn get_document_revision(
+        &self,
+        document_id: Uuid,
+        revision: i64,
+    ) -> Result<Option<DocumentRevision>, WorkspaceError> {
+        let conn = self.conn().await?;
+        let row = conn
+            .query_opt(
+                "SELECT id, document_id, revision, content, content_hash, diff, created_at \
+                 FROM memory_document_revisions WHERE document_id = $1 AND revision = $2",
+                &[&document_id, &(revision as i32)],
+            )
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Revision query failed: {}", e),
+            })?;
+        Ok(row.as_ref().map(Self::row_to_document_revision))
+    }
+
+    fn row_to_document_revision(row: &tokio_postgres::Row) -> DocumentRevision {
+        DocumentRevision {
+            id: row.get("id"),
+            document_id: row.get("document_id"),
+            revision: i64::from(row.get::<_, i32>("revision")),
+            content: row.get("content"),
+            content_hash: row.get("content_hash"),
+            diff: row.get("diff"),
+            created_at: row.get("created_at"),
+        }
+    }
+
     /// Delete a document by its path.
     pub async fn delete_document_by_path(
         &self,
@@ -179,6 +316,171 @@ impl Repository {
         Ok(())
     }
 
+    // ==================== Trash Operations ====================
+
+    /// Move a document into the trash. Its chunks go with the document row.
+    pub async fn trash_document_by_path(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        path: &str,
+        deleted_by: &str,
+    ) -> Result<TrashedDocument, WorkspaceError> {
+        let conn = self.conn().await?;
+
+        let row = conn
+            .query_opt(
+                r#"
+                WITH doc AS (
+                    DELETE FROM memory_documents
+                    WHERE user_id = $1 AND agent_id IS NOT DISTINCT FROM $2 AND path = $3
+                    RETURNING user_id, agent_id, path, content, metadata, created_at, updated_at
+                )
+                INSERT INTO memory_trash
+                    (user_id, agent_id, path, content, metadata, deleted_by,
+                     document_created_at, document_updated_at)
+                SELECT user_id, agent_id, path, content, metadata, $4, created_at, updated_at
+                FROM doc
+                RETURNING id, user_id, agent_id, path, content, metadata, deleted_by,
+                          document_created_at, document_updated_at, deleted_at
+                "#,
+                &[&user_id, &agent_id, &path, &deleted_by],
+            )
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Trash failed: {}", e),
+            })?;
+
+        match row {
+            Some(row) => Ok(Self::row_to_trashed_document(&row)),
+            None => Err(WorkspaceError::DocumentNotFound {
+                doc_type: path.to_string(),
+                user_id: user_id.to_string(),
+            }),
+        }
+    }
+
+    /// Trash entries, most recently deleted first.
+    pub async fn list_trashed_documents(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+    ) -> Result<Vec<TrashedDocument>, WorkspaceError> {
+        let conn = self.conn().await?;
+
+        let rows = conn
+            .query(
+                r#"
+                SELECT id, user_id, agent_id, path, content, metadata, deleted_by,
+                       document_created_at, document_updated_at, deleted_at
+                FROM memory_trash
+                WHERE user_id = $1 AND agent_id IS NOT DISTINCT FROM $2
+                ORDER BY deleted_at DESC, path
+                "#,
+                &[&user_id, &agent_id],
+            )
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Query failed: {}", e),
+            })?;
+
+        Ok(rows.iter().map(Self::row_to_trashed_document).collect())
+    }
+
+    /// Get one trash entry.
+    pub async fn get_trashed_document(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        id: Uuid,
+    ) -> Result<Option<TrashedDocument>, WorkspaceError> {
+        let conn = self.conn().await?;
+
+        let row = conn
+            .query_opt(
+                r#"
+                SELECT id, user_id, agent_id, path, content, metadata, deleted_by,
+                       document_created_at, document_updated_at, deleted_at
+                FROM memory_trash
+                WHERE user_id = $1 AND agent_id IS NOT DISTINCT FROM $2 AND id = $3
+                "#,
+                &[&user_id, &agent_id, &id],
+            )
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Query failed: {}", e),
+            })?;
+
+        Ok(row.as_ref().map(Self::row_to_trashed_document))
+    }
+
+    /// Drop one trash entry.
+    pub async fn delete_trashed_document(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        id: Uuid,
+    ) -> Result<bool, WorkspaceError> {
+        let conn = self.conn().await?;
+
+        let deleted = conn
+            .execute(
+                r#"
+                DELETE FROM memory_trash
+                WHERE user_id = $1 AND agent_id IS NOT DISTINCT FROM $2 AND id = $3
+                "#,
+                &[&user_id, &agent_id, &id],
+            )
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Delete failed: {}", e),
+            })?;
+
+        Ok(deleted > 0)
+    }
+
+    /// Drop trash entries deleted before `cutoff`.
+    pub async fn purge_trashed_documents(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<TrashedDocument>, WorkspaceError> {
+        let conn = self.conn().await?;
+
+        let rows = conn
+            .query(
+                r#"
+                DELETE FROM memory_trash
+                WHERE user_id = $1 AND agent_id IS NOT DISTINCT FROM $2 AND deleted_at < $3
+                RETURNING id, user_id, agent_id, path, content, metadata, deleted_by,
+                          document_created_at, document_updated_at, deleted_at
+                "#,
+                &[&user_id, &agent_id, &cutoff],
+            )
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Purge failed: {}", e),
+            })?;
+
+        Ok(rows.iter().map(Self::row_to_trashed_document).collect())
+    }
+
+    fn row_to_trashed_document(row: &tokio_postgres::Row) -> TrashedDocument {
+        TrashedDocument {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            agent_id: row.get("agent_id"),
+            path: row.get("path"),
+            content: row.get("content"),
+            metadata: row.get("metadata"),
+            deleted_by: row.get("deleted_by"),
+            document_created_at: row.get("document_created_at"),
+            document_updated_at: row.get("document_updated_at"),
+            deleted_at: row.get("deleted_at"),
+        }
+    }
+
     /// List files and directories in a directory path.
     ///
     /// Returns immediate children (not recursive).
@@ -189,7 +491,7 @@ impl Repository {
         agent_id: Option<Uuid>,
         directory: &str,
     ) -> Result<Vec<WorkspaceEntry>, WorkspaceError> {
-        let conn = self.conn().await?;
+        let conn = self.read_conn().await?;
 
         let rows = conn
             .query(
@@ -246,7 +548,7 @@ impl Repository {
         user_id: &str,
         agent_id: Option<Uuid>,
     ) -> Result<Vec<MemoryDocument>, WorkspaceError> {
-        let conn = self.conn().await?;
+        let conn = self.read_conn().await?;
 
         let rows = conn
             .query(
@@ -386,6 +688,105 @@ impl Repository {
             .collect())
     }
 
+    /// Look up a chunk and its document path, scoped to the owner.
+    pub async fn get_chunk(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        chunk_id: Uuid,
+    ) -> Result<Option<CitedChunk>, WorkspaceError> {
+        let conn = self.conn().await?;
+
+        let row = conn
+            .query_opt(
+                r#"
+                SELECT c.id, c.document_id, d.path, c.chunk_index, c.content
+                FROM memory_chunks c
+                JOIN memory_documents d ON d.id = c.document_id
+                WHERE c.id = $1 AND d.user_id = $2 AND d.agent_id IS NOT DISTINCT FROM $3
+                "#,
+                &[&chunk_id, &user_id, &agent_id],
+            )
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Query failed: {}", e),
+            })?;
+
+        Ok(row.map(|row| CitedChunk {
+            chunk_id: row.get("id"),
+            document_id: row.get("document_id"),
+            path: row.get("path"),
+            chunk_index: row.get("chunk_index"),
+            content: row.get("content"),
+        }))
+    }
+
+    /// Aggregate document/chunk storage under an optional path prefix.
+    pub async fn storage_usage(
+        &self,
+        user_id: &str,
+        agent_id: Option<Uuid>,
+        path_prefix: Option<&str>,
+    ) -> Result<WorkspaceStorageUsage, WorkspaceError> {
+        let conn = self.read_conn().await?;
+        let envelope_prefix = crate::legal::workspace_crypto::encrypted_payload_prefix();
+
+        let doc_row = conn
+            .query_one(
+                r#"
+                SELECT
+                    COUNT(*)::bigint AS document_count,
+                    COALESCE(SUM(octet_length(content)), 0)::bigint AS document_bytes,
+                    COUNT(*) FILTER (
+                        WHERE left(content, length($4::text)) = $4::text
+                    )::bigint AS binary_count,
+                    COALESCE(SUM(octet_length(content)) FILTER (
+                        WHERE left(content, length($4::text)) = $4::text
+                    ), 0)::bigint AS binary_bytes
+                FROM memory_documents
+                WHERE user_id = $1 AND agent_id IS NOT DISTINCT FROM $2
+                  AND ($3::text IS NULL OR left(path, length($3::text)) = $3::text)
+                "#,
+                &[&user_id, &agent_id, &path_prefix, &envelope_prefix],
+            )
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Storage usage query failed: {}", e),
+            })?;
+
+        let chunk_row = conn
+            .query_one(
+                r#"
+                SELECT
+                    COUNT(c.id)::bigint AS chunk_count,
+                    COALESCE(SUM(octet_length(c.content)), 0)::bigint AS chunk_bytes,
+                    COUNT(c.embedding)::bigint AS embedded_chunk_count
+                FROM memory_chunks c
+                JOIN memory_documents d ON d.id = c.document_id
+                WHERE d.user_id = $1 AND d.agent_id IS NOT DISTINCT FROM $2
+                  AND ($3::text IS NULL OR left(d.path, length($3::text)) = $3::text)
+                "#,
+                &[&user_id, &agent_id, &path_prefix],
+            )
+            .await
+            .map_err(|e| WorkspaceError::SearchFailed {
+                reason: format!("Storage usage query failed: {}", e),
+            })?;
+
+        let count = |row: &tokio_postgres::Row, column: &str| -> u64 {
+            row.get::<_, i64>(column).max(0) as u64
+        };
+        Ok(WorkspaceStorageUsage {
+            document_count: count(&doc_row, "document_count"),
+            document_bytes: count(&doc_row, "document_bytes"),
+            binary_count: count(&doc_row, "binary_count"),
+            binary_bytes: count(&doc_row, "binary_bytes"),
+            chunk_count: count(&chunk_row, "chunk_count"),
+            chunk_bytes: count(&chunk_row, "chunk_bytes"),
+            embedded_chunk_count: count(&chunk_row, "embedded_chunk_count"),
+        })
+    }
+
     // ==================== Search Operations ====================
 
     /// Perform hybrid search combining FTS and vector similarity.
@@ -398,15 +799,14 @@ impl Repository {
         config: &SearchConfig,
     ) -> Result<Vec<SearchResult>, WorkspaceError> {
         let fts_results = if config.use_fts {
-            self.fts_search(user_id, agent_id, query, config.pre_fusion_limit)
-                .await?
+            self.fts_search(user_id, agent_id, query, config).await?
         } else {
             Vec::new()
         };
 
         let vector_results = if config.use_vector {
             if let Some(embedding) = embedding {
-                self.vector_search(user_id, agent_id, embedding, config.pre_fusion_limit)
+                self.vector_search(user_id, agent_id, embedding, config)
                     .await?
             } else {
                 Vec::new()
@@ -424,23 +824,31 @@ impl Repository {
         user_id: &str,
         agent_id: Option<Uuid>,
         query: &str,
-        limit: usize,
+        config: &SearchConfig,
     ) -> Result<Vec<RankedResult>, WorkspaceError> {
-        let conn = self.conn().await?;
+        let conn = self.read_conn().await?;
+        let path_prefix = config.path_prefix_filter();
 
         let rows = conn
             .query(
                 r#"
-                SELECT c.id as chunk_id, c.document_id, c.content,
+                SELECT c.id as chunk_id, c.document_id, d.path, c.chunk_index, c.content,
                        ts_rank_cd(c.content_tsv, plainto_tsquery('english', $3)) as rank
                 FROM memory_chunks c
                 JOIN memory_documents d ON d.id = c.document_id
                 WHERE d.user_id = $1 AND d.agent_id IS NOT DISTINCT FROM $2
                   AND c.content_tsv @@ plainto_tsquery('english', $3)
+                  AND ($5::text IS NULL OR left(d.path, length($5::text)) = $5::text)
                 ORDER BY rank DESC
                 LIMIT $4
                 "#,
-                &[&user_id, &agent_id, &query, &(limit as i64)],
+                &[
+                    &user_id,
+                    &agent_id,
+                    &query,
+                    &(config.pre_fusion_limit as i64),
+                    &path_prefix,
+                ],
             )
             .await
             .map_err(|e| WorkspaceError::SearchFailed {
@@ -453,6 +861,8 @@ impl Repository {
             .map(|(i, row)| RankedResult {
                 chunk_id: row.get("chunk_id"),
                 document_id: row.get("document_id"),
+                path: row.get("path"),
+                chunk_index: row.get("chunk_index"),
                 content: row.get("content"),
                 rank: (i + 1) as u32,
             })
@@ -465,24 +875,32 @@ impl Repository {
         user_id: &str,
         agent_id: Option<Uuid>,
         embedding: &[f32],
-        limit: usize,
+        config: &SearchConfig,
     ) -> Result<Vec<RankedResult>, WorkspaceError> {
-        let conn = self.conn().await?;
+        let conn = self.read_conn().await?;
         let embedding_vec = Vector::from(embedding.to_vec());
+        let path_prefix = config.path_prefix_filter();
 
         let rows = conn
             .query(
                 r#"
-                SELECT c.id as chunk_id, c.document_id, c.content,
+                SELECT c.id as chunk_id, c.document_id, d.path, c.chunk_index, c.content,
                        1 - (c.embedding <=> $3) as similarity
                 FROM memory_chunks c
                 JOIN memory_documents d ON d.id = c.document_id
                 WHERE d.user_id = $1 AND d.agent_id IS NOT DISTINCT FROM $2
                   AND c.embedding IS NOT NULL
+                  AND ($5::text IS NULL OR left(d.path, length($5::text)) = $5::text)
                 ORDER BY c.embedding <=> $3
                 LIMIT $4
                 "#,
-                &[&user_id, &agent_id, &embedding_vec, &(limit as i64)],
+                &[
+                    &user_id,
+                    &agent_id,
+                    &embedding_vec,
+                    &(config.pre_fusion_limit as i64),
+                    &path_prefix,
+                ],
             )
             .await
             .map_err(|e| WorkspaceError::SearchFailed {
@@ -495,6 +913,8 @@ impl Repository {
             .map(|(i, row)| RankedResult {
                 chunk_id: row.get("chunk_id"),
                 document_id: row.get("document_id"),
+                path: row.get("path"),
+                chunk_index: row.get("chunk_index"),
                 content: row.get("content"),
                 rank: (i + 1) as u32,
             })
@@ -27,6 +27,9 @@ pub struct SearchConfig {
     pub min_score: f32,
     /// Maximum results to fetch from each method before fusion.
     pub pre_fusion_limit: usize,
+    /// Restrict results to documents under this directory (no leading or
+    /// trailing slash). `None` searches the whole workspace.
+    pub path_prefix: Option<String>,
 }
 
 impl Default for SearchConfig {
@@ -38,6 +41,7 @@ impl Default for SearchConfig {
             use_vector: true,
             min_score: 0.0,
             pre_fusion_limit: 50,
+            path_prefix: None,
         }
     }
 }
@@ -74,6 +78,35 @@ impl SearchConfig {
         self.min_score = score.clamp(0.0, 1.0);
         self
     }
+
+    /// Restrict results to documents under a directory prefix.
+    ///
+    /// The prefix is matched on whole path segments, so `matters/acme`
+    /// never matches `matters/acme-2/notes.md`. An empty prefix clears the
+    /// restriction.
+    pub fn with_path_prefix(mut self, prefix: impl AsRef<str>) -> Self {
+        let trimmed = prefix.as_ref().trim().trim_matches('/');
+        self.path_prefix = if trimmed.is_empty() {
+            None
+        } else {
+            Some(trimmed.to_string())
+        };
+        self
+    }
+
+    /// Directory prefix with a trailing slash, ready to bind into a
+    /// `substr(path, 1, length(?)) = ?` style filter.
+    pub fn path_prefix_filter(&self) -> Option<String> {
+        self.path_prefix.as_ref().map(|prefix| format!("{prefix}/"))
+    }
+
+    /// Whether a document path falls inside the configured prefix.
+    pub fn matches_path(&self, path: &str) -> bool {
+        match self.path_prefix_filter() {
+            Some(filter) => path.trim_start_matches('/').starts_with(&filter),
+            None => true,
+        }
+    }
 }
 
 /// A search result with hybrid scoring.
@@ -83,6 +116,10 @@ pub struct SearchResult {
     pub document_id: Uuid,
     /// Chunk ID.
     pub chunk_id: Uuid,
+    /// Workspace path of the containing document.
+    pub path: String,
+    /// Position of the chunk within its document (0-based).
+    pub chunk_index: i32,
     /// Chunk content.
     pub content: String,
     /// Combined RRF score (0.0-1.0 normalized).
@@ -108,6 +145,45 @@ impl SearchResult {
     pub fn is_hybrid(&self) -> bool {
         self.fts_rank.is_some() && self.vector_rank.is_some()
     }
+
+    /// Citation anchor for this chunk (see [`chunk_anchor`]).
+    pub fn anchor(&self) -> String {
+        chunk_anchor(self.chunk_id)
+    }
+}
+
+const CHUNK_ANCHOR_PREFIX: &str = "[[chunk:";
+const CHUNK_ANCHOR_SUFFIX: &str = "]]";
+
+/// Stable citation anchor for a chunk: `[[chunk:<uuid>]]`.
+///
+/// The agent copies these from `memory_search` output into its answers; the
+/// web UI turns them into deep links that highlight the cited passage.
+pub fn chunk_anchor(chunk_id: Uuid) -> String {
+    format!("{CHUNK_ANCHOR_PREFIX}{chunk_id}{CHUNK_ANCHOR_SUFFIX}")
+}
+
+/// Chunk IDs cited in `text`, in order of first appearance.
+///
+/// Malformed anchors are ignored.
+pub fn extract_chunk_anchors(text: &str) -> Vec<Uuid> {
+    let mut ids = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(CHUNK_ANCHOR_PREFIX) {
+        rest = &rest[start + CHUNK_ANCHOR_PREFIX.len()..];
+        let Some(end) = rest.find(CHUNK_ANCHOR_SUFFIX) else {
+            break;
+        };
+        // On a parse failure, keep scanning from just after the prefix so a
+        // well-formed anchor following a malformed one is still found.
+        if let Ok(id) = Uuid::parse_str(&rest[..end]) {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+            rest = &rest[end + CHUNK_ANCHOR_SUFFIX.len()..];
+        }
+    }
+    ids
 }
 
 /// Raw result from a single search method.
@@ -115,6 +191,8 @@ impl SearchResult {
 pub struct RankedResult {
     pub chunk_id: Uuid,
     pub document_id: Uuid,
+    pub path: String,
+    pub chunk_index: i32,
     pub content: String,
     pub rank: u32, // 1-based rank
 }
@@ -143,6 +221,8 @@ pub fn reciprocal_rank_fusion(
     // Track scores and metadata for each chunk
     struct ChunkInfo {
         document_id: Uuid,
+        path: String,
+        chunk_index: i32,
         content: String,
         score: f32,
         fts_rank: Option<u32>,
@@ -162,6 +242,8 @@ pub fn reciprocal_rank_fusion(
             })
             .or_insert(ChunkInfo {
                 document_id: result.document_id,
+                path: result.path,
+                chunk_index: result.chunk_index,
                 content: result.content,
                 score: rrf_score,
                 fts_rank: Some(result.rank),
@@ -180,6 +262,8 @@ pub fn reciprocal_rank_fusion(
             })
             .or_insert(ChunkInfo {
                 document_id: result.document_id,
+                path: result.path,
+                chunk_index: result.chunk_index,
                 content: result.content,
                 score: rrf_score,
                 fts_rank: None,
@@ -193,6 +277,8 @@ pub fn reciprocal_rank_fusion(
         .map(|(chunk_id, info)| SearchResult {
             document_id: info.document_id,
             chunk_id,
+            path: info.path,
+            chunk_index: info.chunk_index,
             content: info.content,
             score: info.score,
             fts_rank: info.fts_rank,
@@ -235,6 +321,8 @@ mod tests {
         RankedResult {
             chunk_id,
             document_id: doc_id,
+            path: "notes.md".to_string(),
+            chunk_index: 0,
             content: format!("content for chunk {}", chunk_id),
             rank,
         }
@@ -388,4 +476,54 @@ mod tests {
         assert!(!vector_only.use_fts);
         assert!(vector_only.use_vector);
     }
+
+    #[test]
+    fn test_search_config_path_prefix_matches_whole_segments() {
+        let config = SearchConfig::default().with_path_prefix("/matters/acme/");
+        assert_eq!(config.path_prefix.as_deref(), Some("matters/acme"));
+        assert_eq!(
+            config.path_prefix_filter().as_deref(),
+            Some("matters/acme/")
+        );
+        assert!(config.matches_path("matters/acme/notes.md"));
+        assert!(!config.matches_path("matters/acme-2/notes.md"));
+        assert!(!config.matches_path("MEMORY.md"));
+
+        let cleared = config.with_path_prefix("  ");
+        assert_eq!(cleared.path_prefix, None);
+        assert!(cleared.matches_path("matters/acme-2/notes.md"));
+    }
+
+    #[test]
+    fn test_chunk_anchors_round_trip() {
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+        let text = format!(
+            "Per the lease {} and the amendment {}, rent is due monthly {}. \
+             Ignore [[chunk:not-a-uuid {}",
+            chunk_anchor(first),
+            chunk_anchor(second),
+            chunk_anchor(first),
+            chunk_anchor(second),
+        );
+        assert_eq!(extract_chunk_anchors(&text), vec![first, second]);
+        assert!(extract_chunk_anchors("no citations here").is_empty());
+        assert!(extract_chunk_anchors("[[chunk:").is_empty());
+    }
+
+    #[test]
+    fn test_rrf_carries_chunk_location() {
+        let config = SearchConfig::default().with_limit(10);
+        let chunk = Uuid::new_v4();
+        let mut fts = make_result(chunk, Uuid::new_v4(), 1);
+        fts.path = "matters/acme/lease.md".to_string();
+        fts.chunk_index = 3;
+
+        let results = reciprocal_rank_fusion(vec![fts.clone()], vec![fts], &config);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].path, "matters/acme/lease.md");
+        assert_eq!(results[0].chunk_index, 3);
+        assert_eq!(results[0].anchor(), format!("[[chunk:{chunk}]]"));
+    }
 }
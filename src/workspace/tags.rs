@@ -0,0 +1,160 @@
+//! Document tag keys, values, and filters.
+//!
+//! Tags are free-form `key` or `key=value` labels on workspace documents
+//! (`hot-doc`, `responsive`, `privilege=attorney-client`). Keys are
+//! normalized to lowercase so review tags from different sources line up;
+//! values keep their case. Filters use the same `key[=value]` syntax,
+//! comma-separated, and a document must carry every filter to match.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::db::DocumentTagRecord;
+
+pub const MAX_TAG_KEY_LEN: usize = 64;
+pub const MAX_TAG_VALUE_LEN: usize = 200;
+
+/// Normalize a tag key: trimmed, lowercased, spaces to `-`. Only letters,
+/// digits, `-`, `_`, and `.` are allowed.
+pub fn normalize_tag_key(raw: &str) -> Result<String, String> {
+    let key = raw.trim().to_lowercase().replace(' ', "-");
+    if key.is_empty() {
+        return Err("tag key must not be empty".to_string());
+    }
+    if key.chars().count() > MAX_TAG_KEY_LEN {
+        return Err(format!(
+            "tag key must be at most {MAX_TAG_KEY_LEN} characters"
+        ));
+    }
+    if !key
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(format!(
+            "tag key '{key}' may only contain letters, digits, '-', '_', and '.'"
+        ));
+    }
+    Ok(key)
+}
+
+/// Normalize an optional tag value; blank values become `None`.
+pub fn normalize_tag_value(raw: Option<&str>) -> Result<Option<String>, String> {
+    let Some(value) = raw.map(str::trim).filter(|v| !v.is_empty()) else {
+        return Ok(None);
+    };
+    if value.chars().count() > MAX_TAG_VALUE_LEN {
+        return Err(format!(
+            "tag value must be at most {MAX_TAG_VALUE_LEN} characters"
+        ));
+    }
+    if value.contains(',') {
+        return Err("tag value must not contain ','".to_string());
+    }
+    Ok(Some(value.to_string()))
+}
+
+/// One `key` or `key=value` filter term.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagFilter {
+    pub key: String,
+    pub value: Option<String>,
+}
+
+impl TagFilter {
+    fn matches(&self, tags: &BTreeMap<&str, Option<&str>>) -> bool {
+        match tags.get(self.key.as_str()) {
+            None => false,
+            Some(value) => self.value.is_none() || self.value.as_deref() == *value,
+        }
+    }
+}
+
+/// Parse a comma-separated `key[=value]` filter list.
+pub fn parse_tag_filters(raw: &str) -> Result<Vec<TagFilter>, String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|term| !term.is_empty())
+        .map(|term| {
+            let (key, value) = match term.split_once('=') {
+                Some((key, value)) => (key, Some(value)),
+                None => (term, None),
+            };
+            Ok(TagFilter {
+                key: normalize_tag_key(key)?,
+                value: normalize_tag_value(value)?,
+            })
+        })
+        .collect()
+}
+
+/// Paths whose tags satisfy every filter.
+pub fn paths_matching_tags(
+    records: &[DocumentTagRecord],
+    filters: &[TagFilter],
+) -> BTreeSet<String> {
+    let mut by_path: BTreeMap<&str, BTreeMap<&str, Option<&str>>> = BTreeMap::new();
+    for record in records {
+        by_path
+            .entry(record.path.as_str())
+            .or_default()
+            .insert(record.key.as_str(), record.value.as_deref());
+    }
+    by_path
+        .into_iter()
+        .filter(|(_, tags)| filters.iter().all(|filter| filter.matches(tags)))
+        .map(|(path, _)| path.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn record(path: &str, key: &str, value: Option<&str>) -> DocumentTagRecord {
+        DocumentTagRecord {
+            id: Uuid::new_v4(),
+            user_id: "default".to_string(),
+            path: path.to_string(),
+            key: key.to_string(),
+            value: value.map(str::to_string),
+            created_by: "default".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn keys_are_normalized_and_validated() {
+        assert_eq!(normalize_tag_key(" Hot Doc ").unwrap(), "hot-doc");
+        assert!(normalize_tag_key("  ").is_err());
+        assert!(normalize_tag_key("privilege=ac").is_err());
+        assert_eq!(normalize_tag_value(Some("  ")).unwrap(), None);
+        assert!(normalize_tag_value(Some("a,b")).is_err());
+    }
+
+    #[test]
+    fn filters_require_every_term() {
+        let records = vec![
+            record("a.md", "responsive", None),
+            record("a.md", "privilege", Some("attorney-client")),
+            record("b.md", "responsive", None),
+            record("c.md", "privilege", Some("work-product")),
+        ];
+        let filters = parse_tag_filters("Responsive, privilege=attorney-client").unwrap();
+        assert_eq!(
+            filters[1],
+            TagFilter {
+                key: "privilege".to_string(),
+                value: Some("attorney-client".to_string()),
+            }
+        );
+        assert_eq!(
+            paths_matching_tags(&records, &filters),
+            BTreeSet::from(["a.md".to_string()])
+        );
+        let privileged = parse_tag_filters("privilege").unwrap();
+        assert_eq!(paths_matching_tags(&records, &privileged).len(), 2);
+    }
+}
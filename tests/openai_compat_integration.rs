@@ -14,8 +14,8 @@ use clawyer::channels::web::sse::SseManager;
 use clawyer::channels::web::ws::WsConnectionTracker;
 use clawyer::error::LlmError;
 use clawyer::llm::{
-    CompletionRequest, CompletionResponse, FinishReason, LlmProvider, ToolCompletionRequest,
-    ToolCompletionResponse,
+    CompletionRequest, CompletionResponse, CompletionStream, FinishReason, LlmProvider,
+    StreamChunk, ToolCompletionRequest, ToolCompletionResponse,
 };
 
 const AUTH_TOKEN: &str = "test-openai-token";
@@ -163,10 +163,89 @@ impl LlmProvider for FixedModelProvider {
     }
 }
 
+/// Provider with native streaming: emits text deltas, then a tool call split
+/// across name and argument fragments.
+struct NativeStreamProvider;
+
+#[async_trait]
+impl LlmProvider for NativeStreamProvider {
+    fn model_name(&self) -> &str {
+        "stream-model"
+    }
+
+    fn cost_per_token(&self) -> (Decimal, Decimal) {
+        (Decimal::ZERO, Decimal::ZERO)
+    }
+
+    async fn complete(&self, _req: CompletionRequest) -> Result<CompletionResponse, LlmError> {
+        panic!("streaming requests must not fall back to complete()");
+    }
+
+    async fn complete_with_tools(
+        &self,
+        _req: ToolCompletionRequest,
+    ) -> Result<ToolCompletionResponse, LlmError> {
+        panic!("streaming requests must not fall back to complete_with_tools()");
+    }
+
+    async fn complete_stream(
+        &self,
+        req: ToolCompletionRequest,
+    ) -> Result<CompletionStream, LlmError> {
+        let mut chunks = vec![
+            Ok(StreamChunk::Text("Checking ".to_string())),
+            Ok(StreamChunk::Text("the docket".to_string())),
+        ];
+        let finish_reason = if let Some(tool) = req.tools.first() {
+            chunks.push(Ok(StreamChunk::ToolCall {
+                index: 0,
+                id: Some("call_stream_1".to_string()),
+                name: Some(tool.name.clone()),
+                arguments: None,
+            }));
+            chunks.push(Ok(StreamChunk::ToolCall {
+                index: 0,
+                id: None,
+                name: None,
+                arguments: Some("{\"case\":".to_string()),
+            }));
+            chunks.push(Ok(StreamChunk::ToolCall {
+                index: 0,
+                id: None,
+                name: None,
+                arguments: Some("\"24-cv-1\"}".to_string()),
+            }));
+            FinishReason::ToolUse
+        } else {
+            FinishReason::Stop
+        };
+        chunks.push(Ok(StreamChunk::Done {
+            input_tokens: 12,
+            output_tokens: 7,
+            finish_reason,
+        }));
+        Ok(Box::pin(futures::stream::iter(chunks)))
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Test helpers
 // ---------------------------------------------------------------------------
 
+/// Parse the JSON payloads of an SSE body, skipping `[DONE]`.
+fn sse_payloads(body: &str) -> Vec<serde_json::Value> {
+    body.lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(str::trim)
+        .filter(|data| !data.is_empty() && *data != "[DONE]")
+        .filter_map(|data| serde_json::from_str(data).ok())
+        .collect()
+}
+
 async fn start_test_server() -> (SocketAddr, Arc<GatewayState>, Arc<MockLlmState>) {
     let mock_state = Arc::new(MockLlmState::default());
 
@@ -181,6 +260,7 @@ async fn start_test_server_with_provider(
 ) -> (SocketAddr, Arc<GatewayState>) {
     let state = Arc::new(GatewayState {
         msg_tx: tokio::sync::RwLock::new(None),
+        intake: Arc::new(clawyer::channels::IntakeQueue::default()),
         sse: SseManager::new(),
         workspace: None,
         session_manager: None,
@@ -197,12 +277,18 @@ async fn start_test_server_with_provider(
         llm_provider: Some(llm_provider),
         skill_registry: None,
         skill_catalog: None,
-        chat_rate_limiter: clawyer::channels::web::server::RateLimiter::new(30, 60),
+        rate_limiter: Default::default(),
         registry_entries: Vec::new(),
         cost_guard: None,
+        review_queue: None,
+        routine_engine: None,
+        webhooks: None,
+        git_mirror: None,
+        events: clawyer::events::EventBus::new(),
         startup_time: std::time::Instant::now(),
         legal_config: None,
         runtime_facts: clawyer::compliance::ComplianceRuntimeFacts::default(),
+        review_token_key: clawyer::channels::web::auth::derive_review_token_key(AUTH_TOKEN),
     });
 
     let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
@@ -414,6 +500,76 @@ async fn test_chat_completions_streaming() {
     assert_eq!(*models, vec![Some("mock-model-v1".to_string())]);
 }
 
+#[tokio::test]
+async fn test_chat_completions_native_streaming_tool_call_deltas() {
+    let (addr, _state) = start_test_server_with_provider(Arc::new(NativeStreamProvider)).await;
+    let url = format!("http://{}/v1/chat/completions", addr);
+
+    let resp = client()
+        .post(&url)
+        .bearer_auth(AUTH_TOKEN)
+        .json(&serde_json::json!({
+            "model": "stream-model",
+            "messages": [{"role": "user", "content": "Look up the case"}],
+            "stream": true,
+            "stream_options": {"include_usage": true},
+            "tools": [{
+                "type": "function",
+                "function": {
+                    "name": "docket_lookup",
+                    "description": "Look up a docket",
+                    "parameters": {"type": "object", "properties": {"case": {"type": "string"}}}
+                }
+            }]
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.headers()
+            .get("x-clawyer-streaming")
+            .and_then(|v| v.to_str().ok()),
+        Some("native")
+    );
+
+    let body = resp.text().await.unwrap();
+    assert!(body.trim_end().ends_with("data: [DONE]"), "body: {body}");
+    let payloads = sse_payloads(&body);
+
+    let content: String = payloads
+        .iter()
+        .filter_map(|p| p["choices"][0]["delta"]["content"].as_str())
+        .collect();
+    assert_eq!(content, "Checking the docket");
+
+    let tool_deltas: Vec<&serde_json::Value> = payloads
+        .iter()
+        .filter_map(|p| p["choices"][0]["delta"]["tool_calls"].get(0))
+        .collect();
+    assert_eq!(tool_deltas.len(), 3);
+    assert_eq!(tool_deltas[0]["id"], "call_stream_1");
+    assert_eq!(tool_deltas[0]["type"], "function");
+    assert_eq!(tool_deltas[0]["function"]["name"], "docket_lookup");
+    assert!(tool_deltas[1].get("id").is_none());
+    let arguments: String = tool_deltas
+        .iter()
+        .filter_map(|d| d["function"]["arguments"].as_str())
+        .collect();
+    assert_eq!(arguments, r#"{"case":"24-cv-1"}"#);
+
+    let finish = payloads
+        .iter()
+        .find_map(|p| p["choices"][0]["finish_reason"].as_str())
+        .expect("finish chunk");
+    assert_eq!(finish, "tool_calls");
+
+    let usage = payloads.last().expect("usage chunk");
+    assert_eq!(usage["choices"].as_array().map(Vec::len), Some(0));
+    assert_eq!(usage["usage"]["total_tokens"], 19);
+}
+
 #[tokio::test]
 async fn test_chat_completions_empty_messages() {
     let (addr, _state, _mock_state) = start_test_server().await;
@@ -671,6 +827,7 @@ async fn test_no_llm_provider_returns_503() {
     // Create state WITHOUT llm_provider
     let state = Arc::new(GatewayState {
         msg_tx: tokio::sync::RwLock::new(None),
+        intake: Arc::new(clawyer::channels::IntakeQueue::default()),
         sse: SseManager::new(),
         workspace: None,
         session_manager: None,
@@ -687,12 +844,18 @@ async fn test_no_llm_provider_returns_503() {
         llm_provider: None, // No LLM!
         skill_registry: None,
         skill_catalog: None,
-        chat_rate_limiter: clawyer::channels::web::server::RateLimiter::new(30, 60),
+        rate_limiter: Default::default(),
         registry_entries: Vec::new(),
         cost_guard: None,
+        review_queue: None,
+        routine_engine: None,
+        webhooks: None,
+        git_mirror: None,
+        events: clawyer::events::EventBus::new(),
         startup_time: std::time::Instant::now(),
         legal_config: None,
         runtime_facts: clawyer::compliance::ComplianceRuntimeFacts::default(),
+        review_token_key: clawyer::channels::web::auth::derive_review_token_key(AUTH_TOKEN),
     });
 
     let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
@@ -45,6 +45,7 @@ fn legal_config_for_tests() -> clawyer::config::LegalConfig {
         conflict_check_enabled: true,
         conflict_file_fallback_enabled: true,
         conflict_reindex_on_startup: false,
+        supervised_mode: false,
         network: clawyer::config::LegalNetworkConfig {
             deny_by_default: true,
             allowed_domains: Vec::new(),
@@ -66,6 +67,8 @@ fn legal_config_for_tests() -> clawyer::config::LegalConfig {
             exclude_from_search: true,
             require_master_key_in_max_lockdown: true,
         },
+        storage: clawyer::config::LegalStorageConfig::default(),
+        llm_routing: clawyer::config::LegalLlmRoutingConfig::default(),
     }
 }
 
@@ -96,6 +99,7 @@ async fn start_test_server_with_store_overrides(
 
     let state = Arc::new(GatewayState {
         msg_tx: tokio::sync::RwLock::new(Some(agent_tx)),
+        intake: Arc::new(clawyer::channels::IntakeQueue::default()),
         sse: SseManager::new(),
         workspace,
         session_manager: None,
@@ -112,12 +116,18 @@ async fn start_test_server_with_store_overrides(
         llm_provider: None,
         skill_registry: None,
         skill_catalog: None,
-        chat_rate_limiter: clawyer::channels::web::server::RateLimiter::new(30, 60),
+        rate_limiter: Default::default(),
         registry_entries: Vec::new(),
         cost_guard: None,
+        review_queue: None,
+        routine_engine: None,
+        webhooks: None,
+        git_mirror: None,
+        events: clawyer::events::EventBus::new(),
         startup_time: std::time::Instant::now(),
         legal_config,
         runtime_facts: clawyer::compliance::ComplianceRuntimeFacts::default(),
+        review_token_key: clawyer::channels::web::auth::derive_review_token_key(AUTH_TOKEN),
     });
 
     let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
@@ -248,6 +258,7 @@ async fn test_ws_broadcast_event_received() {
     state.sse.broadcast(SseEvent::Response {
         content: "agent says hi".to_string(),
         thread_id: "t1".to_string(),
+        citations: Vec::new(),
     });
 
     // The WS client should receive it
@@ -621,6 +632,7 @@ async fn test_ws_multiple_events_in_sequence() {
     state.sse.broadcast(SseEvent::Response {
         content: "done".to_string(),
         thread_id: "t1".to_string(),
+        citations: Vec::new(),
     });
 
     // Receive all 4 in order